@@ -125,39 +125,3 @@ pub fn call_openai(api_key: &str, model: &str, system: &str, user: &str) -> Resu
         output_tokens,
     })
 }
-
-/// Rough token estimate: ~4 chars per token
-pub fn estimate_tokens(text: &str) -> usize {
-    (text.len() / 4).max(1)
-}
-
-/// Estimate cost in USD based on provider/model pricing (per 1M tokens)
-pub fn estimate_cost_usd(
-    provider: &str,
-    model: &str,
-    input_tokens: usize,
-    output_tokens: usize,
-) -> f64 {
-    let (input_price, output_price) = match provider {
-        "ollama" => return 0.0, // local inference, always free
-        "openai" => {
-            if model.contains("gpt-4o-mini") {
-                (0.15, 0.60)
-            } else {
-                (2.50, 10.00) // gpt-4o
-            }
-        }
-        _ => {
-            // anthropic
-            if model.contains("opus") {
-                (15.00, 75.00)
-            } else if model.contains("haiku") {
-                (0.80, 4.00)
-            } else {
-                (3.00, 15.00) // sonnet default
-            }
-        }
-    };
-
-    (input_tokens as f64 * input_price + output_tokens as f64 * output_price) / 1_000_000.0
-}
@@ -1,12 +1,16 @@
 use std::path::Path;
 
 use anyhow::Result;
-use revet_core::{config::AIConfig, Finding, Severity};
-use serde::{Deserialize, Serialize};
+use revet_core::ai::{self, AiEstimate, Batch, PriorityContext};
+use revet_core::{config::AIConfig, Finding};
+use serde::Deserialize;
 use serde_json::Value;
 
 mod client;
 
+pub use client::ApiResponse;
+pub use revet_core::ai::read_snippet;
+
 pub struct AiReasoner {
     config: AIConfig,
     max_cost: f64,
@@ -16,16 +20,13 @@ pub struct AiStats {
     pub findings_enriched: usize,
     pub false_positives: usize,
     pub cost_usd: f64,
-}
-
-#[derive(Serialize)]
-struct FindingContext {
-    id: String,
-    severity: String,
-    message: String,
-    file: String,
-    line: usize,
-    snippet: String,
+    /// Eligible findings left out to stay within the budget (marked
+    /// `ai_skipped = "budget"`)
+    pub skipped_budget: usize,
+    /// Lowest score that was sent, when findings were skipped for budget
+    pub score_cutoff: Option<f64>,
+    /// The preflight estimate the run was planned from
+    pub estimate: AiEstimate,
 }
 
 #[derive(Deserialize)]
@@ -36,16 +37,6 @@ struct AiNote {
     false_positive: bool,
 }
 
-const SYSTEM_PROMPT: &str = r#"You are a senior code reviewer analyzing static analysis findings.
-For each finding, provide a concise explanation of the problem and specific remediation advice.
-
-Respond with a JSON array where each element has:
-- "id": the finding id (copy exactly from input)
-- "note": your explanation and remediation advice in plain text (max 250 chars)
-- "false_positive": true if this is very likely a false positive, false otherwise
-
-Output only a valid JSON array. No markdown fences, no extra text."#;
-
 impl AiReasoner {
     pub fn new(config: AIConfig, max_cost_override: Option<f64>) -> Self {
         let max_cost = max_cost_override.unwrap_or(config.max_cost_per_run);
@@ -69,7 +60,24 @@ impl AiReasoner {
         std::env::var(env_var).ok()
     }
 
-    pub fn enrich(&self, findings: &mut [Finding], repo_root: &Path) -> Result<AiStats> {
+    /// Build the requests for `findings` and estimate their cost without
+    /// calling the API (`--estimate-only`)
+    pub fn estimate(
+        &self,
+        findings: &[Finding],
+        repo_root: &Path,
+        context: &PriorityContext,
+    ) -> Result<AiEstimate> {
+        let plan = ai::plan(findings, repo_root, &self.config, self.max_cost, context)?;
+        Ok(AiEstimate::new(&plan, &self.config, self.max_cost))
+    }
+
+    pub fn enrich(
+        &self,
+        findings: &mut [Finding],
+        repo_root: &Path,
+        context: &PriorityContext,
+    ) -> Result<AiStats> {
         let api_key = match self.resolve_api_key() {
             Some(k) => k,
             None => {
@@ -84,102 +92,53 @@ impl AiReasoner {
                 );
             }
         };
+        self.enrich_with(findings, repo_root, context, |batch| {
+            self.send(&api_key, batch)
+        })
+    }
 
-        // Only enrich warning/error findings that have no suggestion yet
-        // (findings with suggestions are already self-explanatory)
-        let eligible: Vec<usize> = findings
-            .iter()
-            .enumerate()
-            .filter(|(_, f)| {
-                matches!(f.severity, Severity::Warning | Severity::Error) && f.suggestion.is_none()
-            })
-            .map(|(i, _)| i)
-            .collect();
-
-        if eligible.is_empty() {
-            return Ok(AiStats {
-                findings_enriched: 0,
-                false_positives: 0,
-                cost_usd: 0.0,
-            });
-        }
-
-        // Build structured context (no raw file dumps — only snippets)
-        let contexts: Vec<FindingContext> = eligible
-            .iter()
-            .map(|&i| {
-                let f = &findings[i];
-                FindingContext {
-                    id: f.id.clone(),
-                    severity: severity_str(&f.severity).to_string(),
-                    message: f.message.clone(),
-                    file: f.file.to_string_lossy().to_string(),
-                    line: f.line,
-                    snippet: read_snippet(repo_root, f),
-                }
-            })
-            .collect();
-
-        let user_message = serde_json::to_string_pretty(&contexts)?;
-
-        // Pre-flight cost estimate
-        let estimated_input =
-            client::estimate_tokens(SYSTEM_PROMPT) + client::estimate_tokens(&user_message);
-        let estimated_output = eligible.len() * 80;
-        let estimated_cost = client::estimate_cost_usd(
-            &self.config.provider,
-            &self.config.model,
-            estimated_input,
-            estimated_output,
-        );
-
-        if estimated_cost > self.max_cost {
-            anyhow::bail!(
-                "Estimated AI cost ${:.4} exceeds max_cost_per_run ${:.4}. \
-                 Raise with --max-cost or [ai].max_cost_per_run in .revet.toml.",
-                estimated_cost,
-                self.max_cost
-            );
+    /// Enrich the highest-priority `findings` the budget covers, sending
+    /// each request through `send` — the provider call in
+    /// [`enrich`](Self::enrich), a stub in tests
+    pub fn enrich_with<F>(
+        &self,
+        findings: &mut [Finding],
+        repo_root: &Path,
+        context: &PriorityContext,
+        mut send: F,
+    ) -> Result<AiStats>
+    where
+        F: FnMut(&Batch) -> Result<ApiResponse>,
+    {
+        let plan = ai::plan(findings, repo_root, &self.config, self.max_cost, context)?;
+        let estimate = AiEstimate::new(&plan, &self.config, self.max_cost);
+        for &i in &plan.skipped {
+            findings[i].ai_skipped = Some("budget".to_string());
         }
 
-        // Call LLM
-        let response = match self.config.provider.as_str() {
-            "ollama" => {
-                let base_url = self
-                    .config
-                    .base_url
-                    .as_deref()
-                    .unwrap_or("http://localhost:11434");
-                client::call_ollama(base_url, &self.config.model, SYSTEM_PROMPT, &user_message)?
-            }
-            "openai" => {
-                client::call_openai(&api_key, &self.config.model, SYSTEM_PROMPT, &user_message)?
-            }
-            _ => {
-                client::call_anthropic(&api_key, &self.config.model, SYSTEM_PROMPT, &user_message)?
-            }
-        };
-
-        let actual_cost = client::estimate_cost_usd(
-            &self.config.provider,
-            &self.config.model,
-            response.input_tokens,
-            response.output_tokens,
-        );
-
-        // Merge notes back into findings
-        let notes = parse_notes(&response.content);
+        let mut actual_cost = 0.0;
         let mut enriched = 0usize;
         let mut false_positives = 0usize;
 
-        for note in &notes {
-            if let Some(&idx) = eligible.iter().find(|&&i| findings[i].id == note.id) {
-                findings[idx].ai_note = Some(note.note.clone());
-                if note.false_positive {
-                    findings[idx].ai_false_positive = true;
-                    false_positives += 1;
+        for batch in &plan.batches {
+            let response = send(batch)?;
+            actual_cost += ai::estimate_cost_usd(
+                &self.config.provider,
+                &self.config.model,
+                response.input_tokens,
+                response.output_tokens,
+            );
+
+            // Merge notes back into findings
+            for note in parse_notes(&response.content) {
+                if let Some(&idx) = batch.findings.iter().find(|&&i| findings[i].id == note.id) {
+                    findings[idx].ai_note = Some(note.note);
+                    if note.false_positive {
+                        findings[idx].ai_false_positive = true;
+                        false_positives += 1;
+                    }
+                    enriched += 1;
                 }
-                enriched += 1;
             }
         }
 
@@ -187,39 +146,27 @@ impl AiReasoner {
             findings_enriched: enriched,
             false_positives,
             cost_usd: actual_cost,
+            skipped_budget: plan.skipped.len(),
+            score_cutoff: plan.score_cutoff,
+            estimate,
         })
     }
-}
-
-fn read_snippet(repo_root: &Path, finding: &Finding) -> String {
-    if finding.file == std::path::Path::new("") || finding.line == 0 {
-        return String::new();
-    }
-    let path = if finding.file.is_absolute() {
-        finding.file.clone()
-    } else {
-        repo_root.join(&finding.file)
-    };
-    let content = match std::fs::read_to_string(&path) {
-        Ok(c) => c,
-        Err(_) => return String::new(),
-    };
-    let lines: Vec<&str> = content.lines().collect();
-    let start = finding.line.saturating_sub(4);
-    let end = (finding.line + 4).min(lines.len());
-    lines[start..end]
-        .iter()
-        .enumerate()
-        .map(|(i, l)| format!("{:4}: {}", start + i + 1, l))
-        .collect::<Vec<_>>()
-        .join("\n")
-}
 
-fn severity_str(s: &Severity) -> &'static str {
-    match s {
-        Severity::Error => "error",
-        Severity::Warning => "warning",
-        Severity::Info => "info",
+    /// Call the configured provider with one request
+    fn send(&self, api_key: &str, batch: &Batch) -> Result<ApiResponse> {
+        let model = &self.config.model;
+        match self.config.provider.as_str() {
+            "ollama" => {
+                let base_url = self
+                    .config
+                    .base_url
+                    .as_deref()
+                    .unwrap_or("http://localhost:11434");
+                client::call_ollama(base_url, model, batch.system, &batch.user)
+            }
+            "openai" => client::call_openai(api_key, model, batch.system, &batch.user),
+            _ => client::call_anthropic(api_key, model, batch.system, &batch.user),
+        }
     }
 }
 
@@ -0,0 +1,979 @@
+//! `revet api` — read-only HTTP API over the last run and the cached graph
+//!
+//! Serves the newest run log (`runs/` in the state directory) and graph
+//! cache of a repository, or of every repository in a `revet-workspace.toml`, to
+//! internal dashboards. Requests never trigger analysis: a project's data is
+//! reloaded when a newer run log appears, and its graph only when a graph
+//! endpoint first needs it.
+//!
+//! Responses reuse the `--format json` document types. Every data response
+//! carries an `ETag` built from the run IDs it was read from, so pollers can
+//! send `If-None-Match` and get `304 Not Modified` until the next review.
+//! `/openapi.json` is generated from the route table below, with response
+//! schemas derived from the same serde types the responses are written with.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use revet_core::analyzer::complexity::delta::DeltaSummary;
+use revet_core::StoragePaths;
+use revet_core::{
+    CacheFormat, CodeGraph, Confidence, CoverageReport, GraphCache, NodeKind, RevetConfig,
+    WorkspaceConfig, WORKSPACE_FILE,
+};
+use schemars::generate::SchemaSettings;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::output::json::{JsonFinding, JsonSummary};
+use crate::run_log::{self, RunLog, RunSummary};
+
+/// Default `--bind` address
+pub const DEFAULT_BIND: &str = "127.0.0.1:7420";
+
+const SEVERITIES: [&str; 3] = ["error", "warning", "info"];
+
+// ── Response types ───────────────────────────────────────────────
+
+/// `GET /findings`
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FindingsResponse {
+    pub findings: Vec<JsonFinding>,
+}
+
+/// `GET /summary`
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SummaryResponse {
+    /// Totals over `projects`
+    pub summary: JsonSummary,
+    pub projects: Vec<ProjectSummary>,
+}
+
+/// Last run of one project
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ProjectSummary {
+    pub project: String,
+    pub run_id: String,
+    /// Unix seconds
+    pub timestamp: u64,
+    pub files_analyzed: usize,
+    pub summary: JsonSummary,
+}
+
+/// `GET /graph/node`
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NodesResponse {
+    pub nodes: Vec<ApiNode>,
+}
+
+/// A code graph node, with its file relative to the project root
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ApiNode {
+    pub kind: NodeKind,
+    pub name: String,
+    pub file: String,
+    pub line: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    pub public: bool,
+}
+
+/// `GET /graph/dependents`
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DependentsResponse {
+    pub file: String,
+    pub transitive: bool,
+    /// Other files with nodes that depend on the file's nodes, sorted
+    pub dependents: Vec<String>,
+}
+
+/// Body of every 4xx/5xx response
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+// ── Projects ─────────────────────────────────────────────────────
+
+/// One repository served by the API
+struct Project {
+    name: String,
+    config: RevetConfig,
+    paths: StoragePaths,
+    loaded: Mutex<Option<Arc<Snapshot>>>,
+}
+
+/// What one run left on disk: its log, and the graph cache read on demand
+struct Snapshot {
+    run: RunLog,
+    graph: OnceLock<Option<CodeGraph>>,
+}
+
+impl Project {
+    fn new(name: String, root: PathBuf) -> Result<Self> {
+        let config = RevetConfig::find_and_load(&root)
+            .with_context(|| format!("load config of {}", root.display()))?;
+        Ok(Self {
+            name,
+            paths: StoragePaths::resolve(&root, &config),
+            config,
+            loaded: Mutex::new(None),
+        })
+    }
+
+    /// Data of the newest run, reloaded when a newer run log appears
+    fn snapshot(&self) -> Result<Arc<Snapshot>, ApiError> {
+        let Some(id) = run_log::latest_run_id(&self.paths) else {
+            return Err(ApiError::not_found(format!(
+                "no review run recorded for `{}` — run `revet review` first",
+                self.name
+            )));
+        };
+        let mut loaded = self.loaded.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(snapshot) = loaded.as_ref().filter(|s| s.run.id == id) {
+            return Ok(snapshot.clone());
+        }
+        let run = run_log::load_run_log(&self.paths, &id).map_err(ApiError::internal)?;
+        let snapshot = Arc::new(Snapshot {
+            run,
+            graph: OnceLock::new(),
+        });
+        *loaded = Some(snapshot.clone());
+        Ok(snapshot)
+    }
+
+    fn graph<'s>(&self, snapshot: &'s Snapshot) -> Result<&'s CodeGraph, ApiError> {
+        snapshot
+            .graph
+            .get_or_init(|| {
+                GraphCache::at(&self.paths, CacheFormat::from_config(&self.config.cache))
+                    .load()
+                    .ok()
+                    .flatten()
+                    .map(|(graph, _)| graph)
+            })
+            .as_ref()
+            .ok_or_else(|| {
+                ApiError::not_found(format!(
+                    "no cached graph for `{}` — run `revet review` with graph analysis enabled",
+                    self.name
+                ))
+            })
+    }
+}
+
+/// Everything `revet api` serves
+pub struct ApiState {
+    projects: Vec<Project>,
+    workspace: bool,
+}
+
+impl ApiState {
+    /// Serve the repository at `path`, or every repository of the workspace
+    /// when `path` is a `revet-workspace.toml` or a directory containing one
+    pub fn open(path: &Path) -> Result<Self> {
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("{} not found", path.display()))?;
+        let workspace_file = if path.is_file() {
+            Some(path.clone())
+        } else {
+            Some(path.join(WORKSPACE_FILE)).filter(|f| f.is_file())
+        };
+
+        let Some(file) = workspace_file else {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| ".".to_string());
+            return Ok(Self {
+                projects: vec![Project::new(name, path)?],
+                workspace: false,
+            });
+        };
+
+        let root = file.parent().unwrap_or(Path::new("/")).to_path_buf();
+        let repos = WorkspaceConfig::from_file(&file)?.resolve_repos(&root)?;
+        if repos.is_empty() {
+            bail!("{} lists no repositories", file.display());
+        }
+        let projects = repos
+            .into_iter()
+            .map(|repo| Project::new(repo.name, repo.path))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            projects,
+            workspace: true,
+        })
+    }
+
+    /// Names of the served projects, in workspace order
+    pub fn project_names(&self) -> Vec<&str> {
+        self.projects.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    /// Answer a request for `url` (path and query string)
+    pub fn handle(&self, method: &str, url: &str, if_none_match: Option<&str>) -> ApiResponse {
+        if method != "GET" {
+            return ApiError::new(
+                405,
+                format!("{} is not supported; the API is read-only", method),
+            )
+            .into();
+        }
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        if path == "/openapi.json" {
+            return ApiResponse::json(200, &openapi(), None);
+        }
+        let Some(route) = ROUTES.iter().find(|r| r.path == path) else {
+            return ApiError::not_found(format!("no such endpoint: {}", path)).into();
+        };
+        let reply = Query::parse(query, route.params).and_then(|q| (route.handler)(self, &q));
+        match reply {
+            Ok(reply) => {
+                let etag = format!("\"{}\"", reply.run_ids.join("+"));
+                if if_none_match.is_some_and(|header| etag_matches(header, &etag)) {
+                    ApiResponse {
+                        status: 304,
+                        body: String::new(),
+                        etag: Some(etag),
+                    }
+                } else {
+                    ApiResponse::json(200, &reply.body, Some(etag))
+                }
+            }
+            Err(e) => e.into(),
+        }
+    }
+
+    /// Projects named by `?project=`, or all of them
+    fn selected(&self, query: &Query) -> Result<Vec<&Project>, ApiError> {
+        match query.get("project") {
+            Some(name) => Ok(vec![self.project(name)?]),
+            None => Ok(self.projects.iter().collect()),
+        }
+    }
+
+    /// The project named by `?project=`, which only a single repository may
+    /// leave out
+    fn single(&self, query: &Query) -> Result<&Project, ApiError> {
+        match (query.get("project"), self.projects.as_slice()) {
+            (Some(name), _) => self.project(name),
+            (None, [project]) => Ok(project),
+            (None, _) => Err(ApiError::bad_request(format!(
+                "`project` is required in a workspace (one of: {})",
+                self.project_names().join(", ")
+            ))),
+        }
+    }
+
+    fn project(&self, name: &str) -> Result<&Project, ApiError> {
+        self.projects
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| ApiError::not_found(format!("no project named `{}`", name)))
+    }
+
+    /// Snapshots of `projects` that have a run, or 404 when none has
+    fn snapshots<'p>(
+        &self,
+        projects: Vec<&'p Project>,
+    ) -> Result<Vec<(&'p Project, Arc<Snapshot>)>, ApiError> {
+        let mut snapshots = Vec::new();
+        let mut last_error = None;
+        for project in projects {
+            match project.snapshot() {
+                Ok(snapshot) => snapshots.push((project, snapshot)),
+                Err(e) if e.status == 404 => last_error = Some(e),
+                Err(e) => return Err(e),
+            }
+        }
+        match (snapshots.is_empty(), last_error) {
+            (true, Some(e)) => Err(e),
+            _ => Ok(snapshots),
+        }
+    }
+}
+
+// ── Handlers ─────────────────────────────────────────────────────
+
+/// A successful response body and the runs it was read from
+struct Reply {
+    body: Value,
+    run_ids: Vec<String>,
+}
+
+impl Reply {
+    fn new(body: impl Serialize, run_ids: Vec<String>) -> Result<Self, ApiError> {
+        let body = serde_json::to_value(body).map_err(ApiError::internal)?;
+        Ok(Self { body, run_ids })
+    }
+}
+
+fn findings(state: &ApiState, query: &Query) -> Result<Reply, ApiError> {
+    let severities = query.list("severity");
+    if let Some(bad) = severities.iter().find(|s| !SEVERITIES.contains(s)) {
+        return Err(ApiError::bad_request(format!(
+            "severity = {:?} is invalid. Must be one of: {}",
+            bad,
+            SEVERITIES.join(", ")
+        )));
+    }
+    let rules = query.list("rule");
+    let prefix = query.get("path").map(Path::new);
+
+    let snapshots = state.snapshots(state.selected(query)?)?;
+    let mut findings = Vec::new();
+    for (project, snapshot) in &snapshots {
+        let kept = snapshot.run.findings.iter().filter(|f| {
+            !f.suppressed
+                && (severities.is_empty() || severities.contains(&f.severity.as_str()))
+                && (rules.is_empty() || rules.iter().any(|r| rule_matches(r, &f.id, &f.rule)))
+                && prefix.is_none_or(|p| Path::new(&f.file).starts_with(p))
+        });
+        findings.extend(kept.map(|f| JsonFinding {
+            id: f.id.clone(),
+            severity: f.severity.clone(),
+            message: f.message.clone(),
+            file: f.file.clone(),
+            line: f.line,
+            column: None,
+            end_column: None,
+            confidence: Confidence::default(),
+            localized_message: None,
+            repo: state.workspace.then(|| project.name.clone()),
+            change: None,
+            generated: f.generated,
+            code_kind: f.code_kind,
+            skipped_reason: None,
+            rule: f.source_tool.as_ref().and(f.rule.clone()),
+            source_tool: f.source_tool.clone(),
+            details: f.details.clone(),
+        }));
+    }
+    Reply::new(FindingsResponse { findings }, run_ids(&snapshots))
+}
+
+/// `rule` names the finding's sub-rule (`error_handling:swallowed`), its
+/// module (`error_handling`) or its ID prefix (`ERR`)
+fn rule_matches(rule: &str, id: &str, finding_rule: &Option<String>) -> bool {
+    let prefix = id.rsplit_once('-').map_or(id, |(prefix, _)| prefix);
+    prefix == rule
+        || finding_rule
+            .as_deref()
+            .is_some_and(|r| r == rule || r.split(':').next() == Some(rule))
+}
+
+fn summary(state: &ApiState, query: &Query) -> Result<Reply, ApiError> {
+    let snapshots = state.snapshots(state.selected(query)?)?;
+    let projects: Vec<ProjectSummary> = snapshots
+        .iter()
+        .map(|(project, snapshot)| ProjectSummary {
+            project: project.name.clone(),
+            run_id: snapshot.run.id.clone(),
+            timestamp: snapshot.run.timestamp,
+            files_analyzed: snapshot.run.files_analyzed,
+            summary: json_summary(&snapshot.run.summary),
+        })
+        .collect();
+
+    let mut total = JsonSummary {
+        errors: 0,
+        warnings: 0,
+        info: 0,
+        generated: 0,
+        complexity_delta: None,
+        doc_coverage: None,
+        budget: None,
+        memory: None,
+        timings: None,
+        shadow: Vec::new(),
+        session_ignored: None,
+        degraded: Vec::new(),
+    };
+    for p in &projects {
+        total.errors += p.summary.errors;
+        total.warnings += p.summary.warnings;
+        total.info += p.summary.info;
+        total.generated += p.summary.generated;
+        if let Some(delta) = p.summary.complexity_delta {
+            let sum = total
+                .complexity_delta
+                .get_or_insert_with(DeltaSummary::default);
+            sum.reported += delta.reported;
+            sum.deferred += delta.deferred;
+            sum.absolute += delta.absolute;
+        }
+    }
+    Reply::new(
+        SummaryResponse {
+            summary: total,
+            projects,
+        },
+        run_ids(&snapshots),
+    )
+}
+
+fn json_summary(summary: &RunSummary) -> JsonSummary {
+    JsonSummary {
+        errors: summary.errors,
+        warnings: summary.warnings,
+        info: summary.info,
+        generated: summary.generated,
+        complexity_delta: summary.complexity_delta,
+        doc_coverage: summary.doc_coverage.clone(),
+        budget: summary.budget.clone(),
+        memory: summary.memory.clone(),
+        timings: None,
+        shadow: summary.shadow.clone(),
+        session_ignored: summary.session_ignored,
+        degraded: summary.degraded.clone(),
+    }
+}
+
+fn graph_node(state: &ApiState, query: &Query) -> Result<Reply, ApiError> {
+    let project = state.single(query)?;
+    let file = query.relative_path("file")?;
+    let snapshot = project.snapshot()?;
+    let graph = project.graph(&snapshot)?;
+    let root = graph.root_path();
+
+    let mut nodes: Vec<ApiNode> = graph
+        .find_nodes(&root.join(file), query.get("name"))
+        .into_iter()
+        .filter_map(|id| graph.node(id))
+        .map(|node| ApiNode {
+            kind: *node.kind(),
+            name: node.name().to_string(),
+            file: relative(node.file_path(), root),
+            line: node.line(),
+            end_line: node.end_line(),
+            public: node.is_public(),
+        })
+        .collect();
+    if nodes.is_empty() {
+        let what = match query.get("name") {
+            Some(name) => format!("`{}` in {}", name, file.display()),
+            None => file.display().to_string(),
+        };
+        return Err(ApiError::not_found(format!(
+            "no graph node for {} in `{}`",
+            what, project.name
+        )));
+    }
+    nodes.sort_by(|a, b| (a.line, &a.name).cmp(&(b.line, &b.name)));
+    Reply::new(NodesResponse { nodes }, vec![snapshot.run.id.clone()])
+}
+
+fn graph_dependents(state: &ApiState, query: &Query) -> Result<Reply, ApiError> {
+    let project = state.single(query)?;
+    let file = query.relative_path("file")?;
+    let transitive = query.flag("transitive");
+    let snapshot = project.snapshot()?;
+    let graph = project.graph(&snapshot)?;
+    let root = graph.root_path();
+    let abs = root.join(file);
+
+    let ids = graph.find_nodes(&abs, None);
+    if ids.is_empty() {
+        return Err(ApiError::not_found(format!(
+            "{} is not in the graph of `{}`",
+            file.display(),
+            project.name
+        )));
+    }
+    let graph_query = graph.query();
+    let mut dependents = BTreeSet::new();
+    for id in ids {
+        let found = if transitive {
+            graph_query.transitive_dependents(id, None)
+        } else {
+            graph_query.direct_dependents(id)
+        };
+        for node in found.into_iter().filter_map(|d| graph.node(d)) {
+            if node.file_path() != &abs {
+                dependents.insert(relative(node.file_path(), root));
+            }
+        }
+    }
+    Reply::new(
+        DependentsResponse {
+            file: file.display().to_string(),
+            transitive,
+            dependents: dependents.into_iter().collect(),
+        },
+        vec![snapshot.run.id.clone()],
+    )
+}
+
+fn coverage(state: &ApiState, query: &Query) -> Result<Reply, ApiError> {
+    let project = state.single(query)?;
+    let snapshot = project.snapshot()?;
+    let Some(coverage) = &snapshot.run.coverage else {
+        return Err(ApiError::not_found(format!(
+            "the last run of `{}` recorded no coverage — run `revet review --coverage-report`",
+            project.name
+        )));
+    };
+    Reply::new(coverage, vec![snapshot.run.id.clone()])
+}
+
+fn run_ids(snapshots: &[(&Project, Arc<Snapshot>)]) -> Vec<String> {
+    snapshots.iter().map(|(_, s)| s.run.id.clone()).collect()
+}
+
+fn relative(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+// ── Routes ───────────────────────────────────────────────────────
+
+type Handler = fn(&ApiState, &Query) -> Result<Reply, ApiError>;
+
+struct Route {
+    path: &'static str,
+    summary: &'static str,
+    params: &'static [Param],
+    /// OpenAPI schema of the 200 response
+    schema: fn() -> Value,
+    handler: Handler,
+}
+
+struct Param {
+    name: &'static str,
+    description: &'static str,
+    required: bool,
+    flag: bool,
+}
+
+const fn param(name: &'static str, description: &'static str) -> Param {
+    Param {
+        name,
+        description,
+        required: false,
+        flag: false,
+    }
+}
+
+const PROJECT: Param = param(
+    "project",
+    "Workspace repository name (required in a workspace, except for /findings and /summary)",
+);
+
+const ROUTES: &[Route] = &[
+    Route {
+        path: "/findings",
+        summary: "Unsuppressed findings of the last run",
+        params: &[
+            param(
+                "severity",
+                "Comma-separated severities: error, warning, info",
+            ),
+            param(
+                "rule",
+                "Comma-separated sub-rules (`module:rule`), modules or finding ID prefixes",
+            ),
+            param(
+                "path",
+                "Only findings under this path, relative to the project root",
+            ),
+            PROJECT,
+        ],
+        schema: schema::<FindingsResponse>,
+        handler: findings,
+    },
+    Route {
+        path: "/summary",
+        summary: "Finding counts of the last run, per project and in total",
+        params: &[PROJECT],
+        schema: schema::<SummaryResponse>,
+        handler: summary,
+    },
+    Route {
+        path: "/graph/node",
+        summary: "Nodes of a file in the cached graph, optionally by name",
+        params: &[
+            Param {
+                required: true,
+                ..param("file", "File path relative to the project root")
+            },
+            param("name", "Node name"),
+            PROJECT,
+        ],
+        schema: schema::<NodesResponse>,
+        handler: graph_node,
+    },
+    Route {
+        path: "/graph/dependents",
+        summary: "Files that depend on a file in the cached graph",
+        params: &[
+            Param {
+                required: true,
+                ..param("file", "File path relative to the project root")
+            },
+            Param {
+                flag: true,
+                ..param(
+                    "transitive",
+                    "Follow dependents of dependents (default: false)",
+                )
+            },
+            PROJECT,
+        ],
+        schema: schema::<DependentsResponse>,
+        handler: graph_dependents,
+    },
+    Route {
+        path: "/coverage",
+        summary: "Coverage report of the last run (recorded with --coverage-report)",
+        params: &[PROJECT],
+        schema: schema::<CoverageReport>,
+        handler: coverage,
+    },
+];
+
+/// Query parameters of a request, checked against its route
+struct Query {
+    values: HashMap<&'static str, String>,
+}
+
+impl Query {
+    fn parse(query: &str, params: &'static [Param]) -> Result<Self, ApiError> {
+        let mut values = HashMap::new();
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let key = percent_decode(key)?;
+            let Some(param) = params.iter().find(|p| p.name == key) else {
+                let expected: Vec<&str> = params.iter().map(|p| p.name).collect();
+                return Err(ApiError::bad_request(format!(
+                    "unknown query parameter `{}` (expected: {})",
+                    key,
+                    expected.join(", ")
+                )));
+            };
+            let value = percent_decode(value)?;
+            if param.flag && !matches!(value.as_str(), "true" | "false") {
+                return Err(ApiError::bad_request(format!(
+                    "{} = {:?} is invalid. Must be true or false",
+                    key, value
+                )));
+            }
+            if values.insert(param.name, value).is_some() {
+                return Err(ApiError::bad_request(format!(
+                    "query parameter `{}` is given more than once",
+                    key
+                )));
+            }
+        }
+        if let Some(missing) = params
+            .iter()
+            .find(|p| p.required && values.get(p.name).is_none_or(String::is_empty))
+        {
+            return Err(ApiError::bad_request(format!(
+                "query parameter `{}` is required",
+                missing.name
+            )));
+        }
+        Ok(Self { values })
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .get(name)
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Comma-separated values
+    fn list(&self, name: &str) -> Vec<&str> {
+        self.get(name)
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.get(name) == Some("true")
+    }
+
+    /// A path inside the project root
+    fn relative_path(&self, name: &str) -> Result<&Path, ApiError> {
+        let path = Path::new(self.get(name).unwrap_or_default());
+        let escapes = path.components().any(|c| {
+            !matches!(
+                c,
+                std::path::Component::Normal(_) | std::path::Component::CurDir
+            )
+        });
+        if escapes {
+            return Err(ApiError::bad_request(format!(
+                "{} = {:?} must be a path relative to the project root",
+                name,
+                path.display()
+            )));
+        }
+        Ok(path)
+    }
+}
+
+fn percent_decode(s: &str) -> Result<String, ApiError> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' => {
+                let byte = s
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| {
+                        ApiError::bad_request(format!("invalid percent-encoding in {:?}", s))
+                    })?;
+                out.push(byte);
+                i += 2;
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8(out)
+        .map_err(|_| ApiError::bad_request(format!("query {:?} is not valid UTF-8", s)))
+}
+
+/// Whether an `If-None-Match` header value lists `etag`
+fn etag_matches(header: &str, etag: &str) -> bool {
+    header
+        .split(',')
+        .map(|t| t.trim().trim_start_matches("W/"))
+        .any(|t| t == "*" || t == etag)
+}
+
+// ── Responses and errors ─────────────────────────────────────────
+
+/// An HTTP response, independent of the server
+#[derive(Debug)]
+pub struct ApiResponse {
+    pub status: u16,
+    /// JSON, empty for 304
+    pub body: String,
+    pub etag: Option<String>,
+}
+
+impl ApiResponse {
+    fn json(status: u16, body: &impl Serialize, etag: Option<String>) -> Self {
+        Self {
+            status,
+            body: serde_json::to_string_pretty(body).unwrap_or_default(),
+            etag,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, message: String) -> Self {
+        Self { status, message }
+    }
+
+    fn bad_request(message: String) -> Self {
+        Self::new(400, message)
+    }
+
+    fn not_found(message: String) -> Self {
+        Self::new(404, message)
+    }
+
+    fn internal(e: impl std::fmt::Display) -> Self {
+        Self::new(500, e.to_string())
+    }
+}
+
+impl From<ApiError> for ApiResponse {
+    fn from(e: ApiError) -> Self {
+        ApiResponse::json(e.status, &ErrorResponse { error: e.message }, None)
+    }
+}
+
+// ── OpenAPI ──────────────────────────────────────────────────────
+
+/// JSON Schema of a response type, with every subschema inlined
+fn schema<T: JsonSchema>() -> Value {
+    SchemaSettings::draft2020_12()
+        .with(|s| s.inline_subschemas = true)
+        .into_generator()
+        .subschema_for::<T>()
+        .to_value()
+}
+
+/// The OpenAPI description served at `/openapi.json`
+fn openapi() -> Value {
+    let error = |description: &str| {
+        json!({
+            "description": description,
+            "content": { "application/json": { "schema": schema::<ErrorResponse>() } },
+        })
+    };
+    let mut paths = Map::new();
+    for route in ROUTES {
+        let parameters: Vec<Value> = route
+            .params
+            .iter()
+            .map(|p| {
+                json!({
+                    "name": p.name,
+                    "in": "query",
+                    "required": p.required,
+                    "description": p.description,
+                    "schema": { "type": if p.flag { "boolean" } else { "string" } },
+                })
+            })
+            .collect();
+        paths.insert(
+            route.path.to_string(),
+            json!({
+                "get": {
+                    "summary": route.summary,
+                    "parameters": parameters,
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "headers": { "ETag": { "schema": { "type": "string" } } },
+                            "content": { "application/json": { "schema": (route.schema)() } },
+                        },
+                        "304": { "description": "Not modified since the ETag in If-None-Match" },
+                        "400": error("Invalid query parameters"),
+                        "404": error("No such project, run, graph node or coverage report"),
+                    },
+                },
+            }),
+        );
+    }
+    paths.insert(
+        "/openapi.json".to_string(),
+        json!({
+            "get": {
+                "summary": "This description",
+                "responses": { "200": { "description": "OpenAPI 3.1 document" } },
+            },
+        }),
+    );
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "revet API",
+            "version": revet_core::VERSION,
+            "description": "Read-only access to the last review run and the cached code graph",
+        },
+        "paths": paths,
+    })
+}
+
+// ── Server ───────────────────────────────────────────────────────
+
+/// The HTTP listener
+pub struct ApiServer {
+    http: tiny_http::Server,
+}
+
+impl ApiServer {
+    /// Listen on `addr`. Anything but a loopback address needs
+    /// `allow_remote`: the API has no authentication.
+    pub fn bind(addr: SocketAddr, allow_remote: bool) -> Result<Self> {
+        if !addr.ip().is_loopback() && !allow_remote {
+            bail!(
+                "refusing to serve on non-loopback address {} without --allow-remote \
+                 (the API has no authentication)",
+                addr
+            );
+        }
+        let http = tiny_http::Server::http(addr)
+            .map_err(|e| anyhow::anyhow!("failed to bind {}: {}", addr, e))?;
+        Ok(Self { http })
+    }
+
+    /// Bound address (with the actual port when bound to port 0)
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.http.server_addr().to_ip()
+    }
+
+    /// Answer requests until [`shutdown`](Self::shutdown)
+    pub fn serve(&self, state: &ApiState) {
+        for request in self.http.incoming_requests() {
+            let if_none_match = request
+                .headers()
+                .iter()
+                .find(|h| h.field.equiv("If-None-Match"))
+                .map(|h| h.value.to_string());
+            let response = state.handle(
+                request.method().as_str(),
+                request.url(),
+                if_none_match.as_deref(),
+            );
+
+            let mut reply =
+                tiny_http::Response::from_string(response.body).with_status_code(response.status);
+            if response.status != 304 {
+                reply = reply.with_header(header("Content-Type", "application/json"));
+            }
+            if let Some(etag) = &response.etag {
+                reply = reply.with_header(header("ETag", etag));
+            }
+            if response.status == 405 {
+                reply = reply.with_header(header("Allow", "GET"));
+            }
+            let _ = request.respond(reply);
+        }
+    }
+
+    /// Stop [`serve`](Self::serve)
+    pub fn shutdown(&self) {
+        self.http.unblock();
+    }
+}
+
+fn header(name: &str, value: &str) -> tiny_http::Header {
+    tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes())
+        .expect("header names and values are ASCII")
+}
+
+pub fn run(path: Option<&Path>, bind: SocketAddr, allow_remote: bool) -> Result<()> {
+    let state = ApiState::open(path.unwrap_or_else(|| Path::new(".")))?;
+    let server = ApiServer::bind(bind, allow_remote)?;
+    let addr = server.local_addr().unwrap_or(bind);
+
+    eprintln!(
+        "{}",
+        format!("  revet v{} — read-only API", revet_core::VERSION)
+            .bold()
+            .yellow()
+    );
+    eprintln!();
+    eprintln!(
+        "  {} http://{} ({})",
+        "Serving".bold(),
+        addr,
+        state.project_names().join(", ")
+    );
+    eprintln!("  OpenAPI description at http://{}/openapi.json", addr);
+    eprintln!();
+
+    server.serve(&state);
+    Ok(())
+}
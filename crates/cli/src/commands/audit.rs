@@ -0,0 +1,125 @@
+//! `revet audit` — the log of the changes revet made to the working tree
+//!
+//! `verify` checks the log's hash chain, `show` prints its entries. See
+//! [`revet_core::audit`] for what is recorded.
+
+use anyhow::Result;
+use colored::Colorize;
+use revet_core::audit::{self, AuditEntry};
+use revet_core::baseline::is_date;
+use revet_core::StoragePaths;
+use std::path::{Path, PathBuf};
+
+use crate::exit::{self, Outcome};
+use crate::OutputFormat;
+
+fn repo_path(path: Option<&Path>) -> PathBuf {
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf())
+}
+
+pub fn verify(path: Option<&Path>) -> Result<Outcome> {
+    let paths = StoragePaths::discover(&repo_path(path));
+    let verification = audit::verify(&paths)?;
+    if verification.is_intact() {
+        eprintln!(
+            "  {} {} entry(ies), chain intact",
+            "ok".green(),
+            verification.entries
+        );
+        return Ok(Outcome::Success);
+    }
+    for problem in &verification.problems {
+        eprintln!("  {} {}", "tampered".red(), problem);
+    }
+    eprintln!(
+        "  {} problem(s) in {} entries of {}",
+        verification.problems.len(),
+        verification.entries,
+        paths.read_path(revet_core::storage::AUDIT_FILE).display()
+    );
+    Ok(Outcome::AuditTampered {
+        problems: verification.problems.len(),
+    })
+}
+
+pub fn show(path: Option<&Path>, since: Option<&str>, format: Option<OutputFormat>) -> Result<()> {
+    let json = match format {
+        None | Some(OutputFormat::Terminal) => false,
+        Some(OutputFormat::Json) => true,
+        Some(other) => {
+            return Err(exit::usage(format!(
+                "`revet audit show` supports --format terminal or json, not {:?}",
+                other
+            )))
+        }
+    };
+    if let Some(date) = since.filter(|d| !is_date(d)) {
+        return Err(exit::usage(format!(
+            "--since takes a date written YYYY-MM-DD, not {:?}",
+            date
+        )));
+    }
+
+    let paths = StoragePaths::discover(&repo_path(path));
+    let entries = audit::read(&paths)?;
+    let shown = audit::since(&entries, since.unwrap_or(""));
+    if json {
+        println!("{}", serde_json::to_string_pretty(&shown)?);
+        return Ok(());
+    }
+    if shown.is_empty() {
+        println!("  {}", "No audit entries.".dimmed());
+        return Ok(());
+    }
+    for entry in shown {
+        print_entry(entry);
+    }
+    Ok(())
+}
+
+fn print_entry(entry: &AuditEntry) {
+    println!(
+        "  {} {}  revet {}  {}",
+        format!("#{}", entry.seq).bold(),
+        entry.timestamp,
+        entry.version,
+        entry.command.join(" ").dimmed()
+    );
+    if let Some(gap) = &entry.gap {
+        println!(
+            "    {}",
+            format!(
+                "{} command(s) changed files with --no-audit between {} and {}",
+                gap.commands, gap.first, gap.last
+            )
+            .yellow()
+        );
+    }
+    for file in &entry.files {
+        let hash = |h: &Option<String>| {
+            h.as_deref()
+                .map_or("(none)".to_string(), |h| h[..h.len().min(12)].to_string())
+        };
+        let ranges: Vec<String> = file
+            .ranges
+            .iter()
+            .map(|r| format!("{}..{}→{}", r.start, r.end, r.new_len))
+            .collect();
+        println!(
+            "    {}  {} → {}{}",
+            file.path,
+            hash(&file.before),
+            hash(&file.after),
+            if ranges.is_empty() {
+                String::new()
+            } else {
+                format!("  bytes {}", ranges.join(", "))
+            }
+        );
+        if !file.findings.is_empty() {
+            println!("      {}", file.findings.join(", ").dimmed());
+        }
+    }
+    println!();
+}
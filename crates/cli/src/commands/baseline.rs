@@ -1,20 +1,56 @@
 //! Baseline command — snapshot current findings so future reviews only report new ones
+//!
+//! With `--from <format> <file>`, findings of external tools are recorded
+//! as well, the way `revet import` merges them into reviews.
+//!
+//! With `--change`, only the findings of the change checked out are
+//! recorded, in a baseline of its own keyed on the `Change-Id` trailer,
+//! which `revet diff` applies to that change (see [`super::diff`]).
+//!
+//! `revet baseline review` walks through the entries of an existing baseline
+//! (see [`session`]) or, with `--report`, prints how many still match.
+//!
+//! Both send the changes to the baseline since the last full review on the
+//! branch to `[reporting] webhook_url` (see [`crate::webhook`]).
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::Colorize;
+use revet_core::analyzer::ownership::codeowners::CodeOwners;
+use revet_core::analyzer::registry::{prefix_of, Registry};
+use revet_core::baseline::delta::{Delta, Snapshot};
+use revet_core::baseline::{self, entry_statuses, EntryMatch, EntryStatus};
+use revet_core::diff::{DiffAnalyzer, StackChange};
 use revet_core::{
-    discover_files, discover_files_extended, AnalyzerDispatcher, Baseline, CodeGraph, GraphCache,
-    ParserDispatcher, RevetConfig, Severity,
+    discover_files, discover_files_with_dotfiles, AnalyzerDispatcher, Baseline, CodeGraph, Finding,
+    GeneratedFiles, GraphCache, MutationJournal, ParserDispatcher, RevetConfig, Rollout, Severity,
+    StoragePaths,
 };
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, IsTerminal, Write};
 use std::path::Path;
 use std::time::Instant;
 
-pub fn run(path: Option<&Path>, clear: bool) -> Result<()> {
+use crate::exit::{self, Classify};
+use crate::run_log;
+use crate::webhook::{self, DeltaEvent};
+use crate::{ImportArgs, OutputFormat};
+
+pub fn run(
+    path: Option<&Path>,
+    clear: bool,
+    reports: &ImportArgs,
+    no_audit: bool,
+    webhook_dry_run: bool,
+) -> Result<()> {
     let repo_path = path.unwrap_or_else(|| Path::new("."));
     let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
     if clear {
-        let removed = Baseline::clear(&repo_path)?;
+        let paths = StoragePaths::discover(&repo_path);
+        let mut journal = super::journal(&repo_path, &paths, no_audit);
+        let removed = Baseline::clear_at(&paths, &mut journal)?;
+        journal.finish()?;
         if removed {
             eprintln!("  {}", "Baseline cleared.".green());
         } else {
@@ -31,15 +67,171 @@ pub fn run(path: Option<&Path>, clear: bool) -> Result<()> {
     eprintln!();
 
     // ── 1. Config ────────────────────────────────────────────────
-    let config = RevetConfig::find_and_load(&repo_path)?;
+    let config = load_config(&repo_path)?;
+
+    // ── 2. Scan ──────────────────────────────────────────────────
+    let mut findings = scan(&repo_path, &config)?;
+    if !reports.from.is_empty() {
+        let imported = super::import::load(&repo_path, reports)?;
+        revet_core::import::merge(&mut findings, imported);
+    }
+
+    // ── 3. Save Baseline ─────────────────────────────────────────
+    let paths = StoragePaths::resolve(&repo_path, &config);
+    let commit = GraphCache::get_git_commit_hash(&repo_path);
+    let mut baseline = Baseline::from_findings(&findings, &repo_path, commit);
+    if let Some(previous) = Baseline::load_at(&paths)? {
+        baseline.keep_expiry_from(&previous);
+    }
+    let mut journal = super::journal(&repo_path, &paths, no_audit);
+    baseline.save_at(&paths, &mut journal)?;
+    journal.finish()?;
+
+    let errors = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Error)
+        .count();
+    let warnings = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Warning)
+        .count();
+    let info = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Info)
+        .count();
+
+    eprintln!();
+    eprintln!(
+        "  {} ({} errors, {} warnings, {} info)",
+        format!("Baselined {} finding(s)", findings.len())
+            .green()
+            .bold(),
+        errors,
+        warnings,
+        info,
+    );
+    let in_generated = findings.iter().filter(|f| f.generated).count();
+    if in_generated > 0 {
+        eprintln!(
+            "  {}",
+            format!("{} of them in generated files", in_generated).dimmed()
+        );
+    }
+    eprintln!("  Time: {:.1}s", start.elapsed().as_secs_f64());
+    notify_delta(
+        &repo_path,
+        &config,
+        &paths,
+        &baseline,
+        &findings,
+        "baseline",
+        webhook_dry_run,
+    );
+
+    Ok(())
+}
+
+/// Send the changes from the baseline of the last full review on the
+/// current branch to `baseline`, against the `findings` of a full scan
+fn notify_delta(
+    repo_path: &Path,
+    config: &RevetConfig,
+    paths: &StoragePaths,
+    baseline: &Baseline,
+    findings: &[Finding],
+    command: &str,
+    dry_run: bool,
+) {
+    if config.reporting.webhook_url.is_none() && !dry_run {
+        return;
+    }
+    let branch = DiffAnalyzer::new(repo_path)
+        .ok()
+        .and_then(|analyzer| analyzer.current_branch());
+    let snapshot = Snapshot::of(baseline, findings, repo_path, &baseline::today());
+    let owners = CodeOwners::load(repo_path);
+    let (since, delta) = match branch
+        .as_deref()
+        .and_then(|branch| run_log::last_baseline(paths, branch))
+    {
+        Some((id, previous)) => (
+            Some(id),
+            Delta::between(&previous.snapshot, &snapshot, &owners),
+        ),
+        None => (None, Delta::default()),
+    };
+    let event = DeltaEvent::new(repo_path, branch, command, since, &delta);
+    webhook::notify(&config.reporting, &event, dry_run);
+}
+
+/// `revet baseline --change`: baseline what `revet diff` reports for the
+/// change checked out (HEAD against its parent), under its `Change-Id` so
+/// new patchsets of the change still match
+pub fn run_change(path: Option<&Path>, cli: &crate::Cli) -> Result<()> {
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+    let change = StackChange::resolve(&repo_path, "HEAD").environment_error()?;
+    let Some(change_id) = change.change_id.clone() else {
+        return Err(exit::usage(
+            "HEAD has no Change-Id trailer; --change baselines one Gerrit change",
+        ));
+    };
+    eprintln!(
+        "{}",
+        format!(
+            "  revet v{} — creating baseline for {}",
+            revet_core::VERSION,
+            change.label()
+        )
+        .bold()
+    );
+    eprintln!();
+
+    let config = load_config(&repo_path)?;
+    let findings = super::diff::change_findings(&repo_path, &change, cli)?;
+
+    let path = StoragePaths::resolve(&repo_path, &config).change_baseline_path(&change_id);
+    let mut baseline = Baseline::from_findings(&findings, &repo_path, Some(change.commit));
+    if let Some(previous) = Baseline::load_from(&path)? {
+        baseline.keep_expiry_from(&previous);
+    }
+    let mut journal = cli.journal(&repo_path, &StoragePaths::resolve(&repo_path, &config));
+    baseline.save_to(&path, &mut journal)?;
+    journal.finish()?;
 
-    // ── 2. File Discovery (always full scan for baseline) ────────
-    let dispatcher = ParserDispatcher::new();
-    let analyzer_dispatcher = AnalyzerDispatcher::new_with_config(&config);
+    eprintln!();
+    eprintln!(
+        "  {} of change {}",
+        format!("Baselined {} finding(s)", findings.len())
+            .green()
+            .bold(),
+        change_id
+    );
+    Ok(())
+}
+
+fn load_config(repo_path: &Path) -> Result<RevetConfig> {
+    let config = RevetConfig::find_and_load(repo_path).usage_error()?;
+    if let Err(conflicts) = Registry::from_config(&config) {
+        return Err(exit::usage(conflicts.join("\n")));
+    }
+    Ok(config)
+}
+
+/// Findings of a full scan, as a baseline records them
+fn scan(repo_path: &Path, config: &RevetConfig) -> Result<Vec<Finding>> {
+    let repo_path = repo_path.to_path_buf();
+
+    // ── File Discovery (always full scan for baseline) ───────────
+    let dispatcher = ParserDispatcher::new_with_config(config);
+    let analyzer_dispatcher = AnalyzerDispatcher::new_with_config(config)
+        .with_rollout(Rollout::resolve(config, &repo_path, true));
     let extensions = dispatcher.supported_extensions();
 
-    let extra_exts = analyzer_dispatcher.extra_extensions(&config);
-    let extra_names = analyzer_dispatcher.extra_filenames(&config);
+    let extra_exts = analyzer_dispatcher.extra_extensions(config);
+    let extra_names = analyzer_dispatcher.extra_filenames(config);
+    let extra_dotfiles = analyzer_dispatcher.extra_dotfiles(config);
     let mut all_extensions: Vec<&str> = extensions.clone();
     for ext in &extra_exts {
         if !all_extensions.contains(ext) {
@@ -48,19 +240,20 @@ pub fn run(path: Option<&Path>, clear: bool) -> Result<()> {
     }
 
     eprint!("  Discovering files (full scan)... ");
-    let files = if extra_names.is_empty() {
+    let files = if extra_names.is_empty() && extra_dotfiles.is_empty() {
         discover_files(&repo_path, &all_extensions, &config.ignore.paths)?
     } else {
-        discover_files_extended(
+        discover_files_with_dotfiles(
             &repo_path,
             &all_extensions,
             &extra_names,
+            &extra_dotfiles,
             &config.ignore.paths,
         )?
     };
     eprintln!("{} ({} files)", "done".green(), files.len());
 
-    // ── 3. Parse ─────────────────────────────────────────────────
+    // ── Parse ────────────────────────────────────────────────────
     eprint!("  Building code graph... ");
     let mut graph = CodeGraph::new(repo_path.clone());
     for file in &files {
@@ -69,40 +262,556 @@ pub fn run(path: Option<&Path>, clear: bool) -> Result<()> {
     let node_count: usize = graph.nodes().count();
     eprintln!("{} ({} nodes)", "done".green(), node_count);
 
-    // ── 4. Domain Analyzers ──────────────────────────────────────
+    // ── Domain Analyzers ─────────────────────────────────────────
+    // Generated files are analyzed whatever `[files] generated` says, so
+    // switching the policy to `analyze` later finds their findings baselined
     eprint!("  Running domain analyzers... ");
-    let findings = analyzer_dispatcher.run_all(&files, &repo_path, &config);
+    // Modules sampled out of their rollout report nothing, so nothing of
+    // theirs is baselined
+    let (mut findings, _) =
+        analyzer_dispatcher.split_shadow(analyzer_dispatcher.run_all(&files, &repo_path, config));
+    let generated = GeneratedFiles::from_config(&config.files).classify(&files);
+    for f in &mut findings {
+        f.generated = generated.contains(&f.file, &repo_path);
+    }
     eprintln!("{} ({} findings)", "done".green(), findings.len());
 
-    // ── 5. Save Baseline ─────────────────────────────────────────
-    let commit = GraphCache::get_git_commit_hash(&repo_path);
-    let baseline = Baseline::from_findings(&findings, &repo_path, commit);
-    baseline.save(&repo_path)?;
+    Ok(findings)
+}
 
-    let errors = findings
-        .iter()
-        .filter(|f| f.severity == Severity::Error)
-        .count();
-    let warnings = findings
-        .iter()
-        .filter(|f| f.severity == Severity::Warning)
-        .count();
-    let info = findings
+// ── Review ───────────────────────────────────────────────────────
+
+/// What `revet baseline review` does with an entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    Keep,
+    /// Remove the entry so its finding resurfaces
+    Expire,
+    /// Keep the entry until this date (`YYYY-MM-DD`)
+    ExpireOn(String),
+    /// Replace the entry with an inline `revet-ignore` comment giving this
+    /// reason
+    Suppress(String),
+}
+
+/// A baseline's entries with their status against the current tree
+pub struct Review<'a> {
+    pub baseline: &'a Baseline,
+    pub statuses: &'a [EntryMatch],
+    pub findings: &'a [Finding],
+    pub repo_root: &'a Path,
+}
+
+impl Review<'_> {
+    /// Entry indices grouped by rule, in rule order
+    fn groups(&self) -> BTreeMap<&str, Vec<usize>> {
+        let mut groups: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (i, entry) in self.baseline.entries.iter().enumerate() {
+            groups.entry(rule_of(entry)).or_default().push(i);
+        }
+        groups
+    }
+
+    /// The current finding an entry matches
+    fn finding(&self, entry: usize) -> Option<&Finding> {
+        self.statuses[entry].finding.map(|i| &self.findings[i])
+    }
+
+    /// `40 still match, 3 no longer match, 2 file deleted`
+    fn breakdown(&self, entries: &[usize]) -> String {
+        [
+            EntryStatus::Matched,
+            EntryStatus::Unmatched,
+            EntryStatus::FileDeleted,
+        ]
         .iter()
-        .filter(|f| f.severity == Severity::Info)
-        .count();
+        .filter_map(|status| {
+            let n = entries
+                .iter()
+                .filter(|&&i| self.statuses[i].status == *status)
+                .count();
+            (n > 0).then(|| format!("{} {}", n, status.as_str()))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+/// Rule an entry is grouped and suppressed under; legacy entries have none
+fn rule_of(entry: &revet_core::BaselineEntry) -> &str {
+    entry.rule.as_deref().unwrap_or("(no rule)")
+}
+
+/// `revet baseline review`: interactively, or a status report with `--report`
+pub fn review(
+    path: Option<&Path>,
+    report: bool,
+    format: Option<OutputFormat>,
+    no_audit: bool,
+    webhook_dry_run: bool,
+) -> Result<()> {
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
+    let json = match format {
+        None | Some(OutputFormat::Terminal) => false,
+        Some(OutputFormat::Json) if report => true,
+        Some(other) => {
+            return Err(exit::usage(format!(
+                "`revet baseline review --report` supports --format terminal or json, not {:?}",
+                other
+            )))
+        }
+    };
+    if !report && !std::io::stdin().is_terminal() {
+        return Err(exit::usage(
+            "`revet baseline review` is interactive; run it in a terminal, or use --report",
+        ));
+    }
+
+    let config = load_config(&repo_path)?;
+    let paths = StoragePaths::resolve(&repo_path, &config);
+    let baseline = Baseline::load_at(&paths)?
+        .ok_or_else(|| exit::usage("No baseline to review. Run `revet baseline` first."))?;
+
+    eprintln!(
+        "{}",
+        format!("  revet v{} — reviewing baseline", revet_core::VERSION).bold()
+    );
     eprintln!();
+    let findings = scan(&repo_path, &config)?;
+    let statuses = entry_statuses(&baseline, &findings, &repo_path);
+    let review = Review {
+        baseline: &baseline,
+        statuses: &statuses,
+        findings: &findings,
+        repo_root: &repo_path,
+    };
+
+    if report {
+        let report = StatusReport::new(&review, &paths.baseline_read_path().to_string_lossy());
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            report.print();
+        }
+        return Ok(());
+    }
+
+    let stdin = std::io::stdin();
+    let mut input = stdin.lock();
+    let mut out = std::io::stderr();
+    let decisions = session(&review, &mut input, &mut out)?;
+    if !summarize(&review, &decisions, &mut out)? {
+        return Ok(());
+    }
+    let answer = ask(&mut input, &mut out, "  Apply these changes? [y/N] ")?;
+    if !matches!(answer.as_deref().map(str::trim), Some("y" | "Y" | "yes")) {
+        eprintln!("  {}", "Nothing changed.".dimmed());
+        return Ok(());
+    }
+    let mut journal = super::journal(&repo_path, &paths, no_audit);
+    apply(&review, &decisions, &paths, &mut journal)?;
+    journal.finish()?;
     eprintln!(
-        "  {} ({} errors, {} warnings, {} info)",
-        format!("Baselined {} finding(s)", findings.len())
-            .green()
-            .bold(),
-        errors,
-        warnings,
-        info,
+        "  {} {}",
+        "Updated".green(),
+        paths.baseline_path().display()
     );
-    eprintln!("  Time: {:.1}s", start.elapsed().as_secs_f64());
+    if let Some(updated) = Baseline::load_at(&paths)? {
+        notify_delta(
+            &repo_path,
+            &config,
+            &paths,
+            &updated,
+            &findings,
+            "baseline review",
+            webhook_dry_run,
+        );
+    }
+    Ok(())
+}
+
+/// Print `prompt` and read one line; `None` at end of input
+fn ask(input: &mut dyn BufRead, out: &mut dyn Write, prompt: &str) -> Result<Option<String>> {
+    write!(out, "{}", prompt)?;
+    out.flush()?;
+    let mut line = String::new();
+    if input
+        .read_line(&mut line)
+        .context("failed to read the answer")?
+        == 0
+    {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+/// Ask for an expiry date after today; `None` at end of input
+fn ask_date(input: &mut dyn BufRead, out: &mut dyn Write) -> Result<Option<String>> {
+    let today = baseline::today();
+    loop {
+        let Some(date) = ask(input, out, "  Expires on (YYYY-MM-DD): ")? else {
+            return Ok(None);
+        };
+        if !baseline::is_date(&date) {
+            writeln!(out, "  {} is not a date like 2030-01-31", date)?;
+        } else if date <= today {
+            writeln!(out, "  Pick a date after today ({})", today)?;
+        } else {
+            return Ok(Some(date));
+        }
+    }
+}
 
+/// Walk through the entries of `review` group by group, reading answers
+/// from `input`. Returns one decision per entry; entries not reached are
+/// kept. Ends early on `q` or at end of input.
+pub fn session(
+    review: &Review,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> Result<Vec<Decision>> {
+    let entries = &review.baseline.entries;
+    let mut decisions = vec![Decision::Keep; entries.len()];
+
+    'groups: for (rule, group) in review.groups() {
+        let mut pending = group;
+        while !pending.is_empty() {
+            writeln!(out)?;
+            writeln!(
+                out,
+                "  {} — {} entr{}: {}",
+                rule.bold(),
+                pending.len(),
+                if pending.len() == 1 { "y" } else { "ies" },
+                review.breakdown(&pending)
+            )?;
+            let Some(answer) = ask(
+                input,
+                out,
+                "  [r]eview each  [k]eep all  e[x]pire all  [d]ate all  [q]uit (add a path to act on part, e.g. `x legacy/`): ",
+            )?
+            else {
+                break 'groups;
+            };
+            let mut words = answer.split_whitespace();
+            let action = words.next().unwrap_or("r");
+            let scope: Vec<usize> = match words.next() {
+                Some(dir) => {
+                    let dir = dir.trim_start_matches("./");
+                    pending
+                        .iter()
+                        .copied()
+                        .filter(|&i| entries[i].file.starts_with(dir))
+                        .collect()
+                }
+                None => pending.clone(),
+            };
+            if scope.is_empty() {
+                writeln!(out, "  No {} entries under that path", rule)?;
+                continue;
+            }
+
+            match action {
+                "k" => {}
+                "x" => {
+                    for &i in &scope {
+                        decisions[i] = Decision::Expire;
+                    }
+                }
+                "d" => {
+                    let Some(date) = ask_date(input, out)? else {
+                        break 'groups;
+                    };
+                    for &i in &scope {
+                        decisions[i] = Decision::ExpireOn(date.clone());
+                    }
+                }
+                "r" => {
+                    for (n, &i) in scope.iter().enumerate() {
+                        writeln!(out)?;
+                        write!(out, "  [{}/{}] ", n + 1, scope.len())?;
+                        match review_entry(review, i, input, out)? {
+                            Some(decision) => decisions[i] = decision,
+                            None => break 'groups,
+                        }
+                    }
+                }
+                "q" => break 'groups,
+                other => {
+                    writeln!(out, "  Unknown action `{}`", other)?;
+                    continue;
+                }
+            }
+            pending.retain(|i| !scope.contains(i));
+        }
+    }
+    Ok(decisions)
+}
+
+/// Show one entry and ask what to do with it; `None` to stop reviewing
+fn review_entry(
+    review: &Review,
+    i: usize,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> Result<Option<Decision>> {
+    let entry = &review.baseline.entries[i];
+    let finding = review.finding(i);
+    let status = match finding {
+        Some(f) => format!("still matches at line {}", f.line),
+        None => review.statuses[i].status.as_str().to_string(),
+    };
+    writeln!(out, "{} — {}", entry.file.bold(), status)?;
+    writeln!(out, "  {}", entry.message)?;
+    if let Some(date) = &entry.expires {
+        writeln!(out, "  {}", format!("expires {}", date).dimmed())?;
+    }
+    if let Some(f) = finding {
+        snippet(&f.file, f.line, out)?;
+    }
+
+    loop {
+        let Some(answer) = ask(
+            input,
+            out,
+            "  [k]eep  e[x]pire now  [d]ate  [s]uppress inline  [o]pen in editor  [q]uit: ",
+        )?
+        else {
+            return Ok(None);
+        };
+        match answer.as_str() {
+            "" | "k" => return Ok(Some(Decision::Keep)),
+            "x" => return Ok(Some(Decision::Expire)),
+            "d" => return Ok(ask_date(input, out)?.map(Decision::ExpireOn)),
+            "s" if finding.is_none() => {
+                writeln!(
+                    out,
+                    "  Only entries that still match a finding can be suppressed inline"
+                )?;
+            }
+            "s" => loop {
+                let Some(reason) = ask(input, out, "  Reason: ")? else {
+                    return Ok(None);
+                };
+                if !reason.is_empty() {
+                    return Ok(Some(Decision::Suppress(reason)));
+                }
+            },
+            "o" => {
+                let line = finding.map_or(1, |f| f.line);
+                if let Err(e) = open_in_editor(&review.repo_root.join(&entry.file), line) {
+                    writeln!(out, "  {:#}", e)?;
+                }
+            }
+            "q" => return Ok(None),
+            other => writeln!(out, "  Unknown action `{}`", other)?,
+        }
+    }
+}
+
+/// Two lines either side of `line` in `file`
+fn snippet(file: &Path, line: usize, out: &mut dyn Write) -> Result<()> {
+    let Ok(content) = std::fs::read_to_string(file) else {
+        return Ok(());
+    };
+    let first = line.saturating_sub(2).max(1);
+    for (number, text) in content.lines().enumerate().skip(first - 1).take(5) {
+        let number = number + 1;
+        let marker = if number == line { ">" } else { " " };
+        writeln!(
+            out,
+            "  {} {:>5} │ {}",
+            marker,
+            number.to_string().dimmed(),
+            text
+        )?;
+    }
     Ok(())
 }
+
+/// Open `file` at `line` in `$VISUAL` or `$EDITOR` (default `vi`)
+fn open_in_editor(file: &Path, line: usize) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    std::process::Command::new(program)
+        .args(words)
+        .arg(format!("+{}", line))
+        .arg(file)
+        .status()
+        .with_context(|| format!("failed to run {}", program))?;
+    Ok(())
+}
+
+/// Print the changes `decisions` make to the baseline and the source
+/// files. Returns whether there are any.
+pub fn summarize(review: &Review, decisions: &[Decision], out: &mut dyn Write) -> Result<bool> {
+    let (mut removed, mut dated, mut suppressed) = (0, 0, 0);
+    writeln!(out)?;
+    for (entry, decision) in review.baseline.entries.iter().zip(decisions) {
+        let label = format!("{}  {}: {}", rule_of(entry), entry.file, entry.message);
+        match decision {
+            Decision::Keep => continue,
+            Decision::Expire => {
+                removed += 1;
+                writeln!(out, "  {}", format!("- {}", label).red())?;
+            }
+            Decision::ExpireOn(date) => {
+                dated += 1;
+                writeln!(
+                    out,
+                    "  {}",
+                    format!("~ {} (expires {})", label, date).yellow()
+                )?;
+            }
+            Decision::Suppress(reason) => {
+                suppressed += 1;
+                writeln!(out, "  {}", format!("- {}", label).red())?;
+                writeln!(
+                    out,
+                    "  {}",
+                    format!("+ inline revet-ignore {} — {}", rule_of(entry), reason).green()
+                )?;
+            }
+        }
+    }
+    if removed + dated + suppressed == 0 {
+        writeln!(out, "  {}", "No changes.".dimmed())?;
+        return Ok(false);
+    }
+    writeln!(
+        out,
+        "  {} expired, {} given an expiry date, {} suppressed inline",
+        removed, dated, suppressed
+    )?;
+    Ok(true)
+}
+
+/// Write `decisions`: inline suppressions into the source files, then the
+/// baseline without expired and suppressed entries, in one replace. Every
+/// write is recorded in `journal`.
+pub fn apply(
+    review: &Review,
+    decisions: &[Decision],
+    paths: &StoragePaths,
+    journal: &mut MutationJournal,
+) -> Result<()> {
+    let mut directives: HashMap<&Path, Vec<(usize, String, String)>> = HashMap::new();
+    let mut ids: HashMap<&Path, Vec<String>> = HashMap::new();
+    for (i, decision) in decisions.iter().enumerate() {
+        let (Decision::Suppress(reason), Some(finding)) = (decision, review.finding(i)) else {
+            continue;
+        };
+        let rule = review.baseline.entries[i]
+            .rule
+            .clone()
+            .unwrap_or_else(|| prefix_of(&finding.id).to_string());
+        ids.entry(finding.file.as_path())
+            .or_default()
+            .push(finding.id.clone());
+        directives.entry(finding.file.as_path()).or_default().push((
+            finding.line,
+            rule,
+            reason.clone(),
+        ));
+    }
+    for (file, directives) in directives {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let updated = revet_core::suppress::insert_directives(file, &content, &directives);
+        journal.write(file, &updated, &ids[file])?;
+    }
+
+    let mut updated = review.baseline.clone();
+    updated.entries = review
+        .baseline
+        .entries
+        .iter()
+        .zip(decisions)
+        .filter_map(|(entry, decision)| match decision {
+            Decision::Keep => Some(entry.clone()),
+            Decision::ExpireOn(date) => {
+                let mut entry = entry.clone();
+                entry.expires = Some(date.clone());
+                Some(entry)
+            }
+            Decision::Expire | Decision::Suppress(_) => None,
+        })
+        .collect();
+    updated.count = updated.entries.len();
+    updated.save_at(paths, journal)
+}
+
+/// Entry counts by status, for `--report`
+#[derive(Debug, Default, Serialize)]
+pub struct StatusCounts {
+    pub entries: usize,
+    pub matched: usize,
+    pub unmatched: usize,
+    pub file_deleted: usize,
+    /// Past their expiry date: their findings are reported again
+    pub expired: usize,
+}
+
+/// `revet baseline review --report`
+#[derive(Debug, Serialize)]
+pub struct StatusReport {
+    pub baseline: String,
+    #[serde(flatten)]
+    pub total: StatusCounts,
+    pub rules: BTreeMap<String, StatusCounts>,
+}
+
+impl StatusReport {
+    pub fn new(review: &Review, baseline: &str) -> Self {
+        let today = baseline::today();
+        let mut total = StatusCounts::default();
+        let mut rules: BTreeMap<String, StatusCounts> = BTreeMap::new();
+        for (entry, status) in review.baseline.entries.iter().zip(review.statuses) {
+            let rule = rules.entry(rule_of(entry).to_string()).or_default();
+            for counts in [&mut total, rule] {
+                counts.entries += 1;
+                match status.status {
+                    EntryStatus::Matched => counts.matched += 1,
+                    EntryStatus::Unmatched => counts.unmatched += 1,
+                    EntryStatus::FileDeleted => counts.file_deleted += 1,
+                }
+                if entry.is_expired(&today) {
+                    counts.expired += 1;
+                }
+            }
+        }
+        Self {
+            baseline: baseline.to_string(),
+            total,
+            rules,
+        }
+    }
+
+    fn print(&self) {
+        println!(
+            "Baseline: {} ({} entries)",
+            self.baseline, self.total.entries
+        );
+        let width = self.rules.keys().map(String::len).max().unwrap_or(0).max(5);
+        println!(
+            "  {:<width$}  {:>7}  {:>7}  {:>9}  {:>12}  {:>7}",
+            "rule", "entries", "matched", "unmatched", "file deleted", "expired"
+        );
+        let rows = self
+            .rules
+            .iter()
+            .map(|(rule, counts)| (rule.as_str(), counts))
+            .chain([("total", &self.total)]);
+        for (rule, c) in rows {
+            println!(
+                "  {:<width$}  {:>7}  {:>7}  {:>9}  {:>12}  {:>7}",
+                rule, c.entries, c.matched, c.unmatched, c.file_deleted, c.expired
+            );
+        }
+    }
+}
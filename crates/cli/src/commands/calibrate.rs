@@ -0,0 +1,167 @@
+//! `revet calibrate` — threshold suggestions from the repository's own
+//! metric distributions
+//!
+//! Runs the threshold-based analyzers in measurement mode (see
+//! [`revet_core::calibrate`]): no findings are produced, only the raw
+//! distributions and how many findings each candidate threshold would give.
+//! The suggestions are printed as a commented `[calibration]` section;
+//! nothing is written to `.revet.toml`.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use revet_core::calibrate::{self, Budget, Metric};
+use revet_core::{
+    discover_files, discover_files_with_dotfiles, AnalyzerDispatcher, CodeGraph, ParserDispatcher,
+    RevetConfig,
+};
+use std::path::Path;
+
+use crate::exit::{self, Classify};
+
+pub fn run(
+    path: Option<&Path>,
+    percentile: Option<u8>,
+    max_findings: Option<usize>,
+    output: Option<&Path>,
+) -> Result<()> {
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+    let mut config = RevetConfig::find_and_load(&repo_path).usage_error()?;
+
+    if let Some(p) = percentile {
+        if !(1..=99).contains(&p) {
+            return Err(exit::usage(format!(
+                "--percentile {} is invalid. Must be between 1 and 99",
+                p
+            )));
+        }
+        config.calibration.percentile = p;
+        config.calibration.max_findings = None;
+    }
+    if max_findings.is_some() {
+        config.calibration.max_findings = max_findings;
+    }
+    let budget = Budget::from_config(&config.calibration);
+
+    let metrics = measure(&repo_path, &config)?;
+    eprintln!();
+    print_report(&metrics, &budget);
+
+    let section = calibrate::toml_section(&metrics, &budget);
+    match output {
+        Some(file) => {
+            std::fs::write(file, &section)
+                .with_context(|| format!("failed to write {}", file.display()))?;
+            eprintln!("  Suggestions written to {}", file.display());
+        }
+        None => {
+            println!();
+            print!("{}", section);
+        }
+    }
+    Ok(())
+}
+
+/// Discover and parse the repository, then measure every enabled analyzer
+fn measure(repo_path: &Path, config: &RevetConfig) -> Result<Vec<Metric>> {
+    let parsers = ParserDispatcher::new_with_config(config);
+    let analyzers = AnalyzerDispatcher::new_with_config(config);
+    let mut extensions = parsers.supported_extensions();
+    for ext in analyzers.extra_extensions(config) {
+        if !extensions.contains(&ext) {
+            extensions.push(ext);
+        }
+    }
+    let extra_names = analyzers.extra_filenames(config);
+    let extra_dotfiles = analyzers.extra_dotfiles(config);
+
+    eprint!("  Discovering files... ");
+    let files = if extra_names.is_empty() && extra_dotfiles.is_empty() {
+        discover_files(repo_path, &extensions, &config.ignore.paths)?
+    } else {
+        discover_files_with_dotfiles(
+            repo_path,
+            &extensions,
+            &extra_names,
+            &extra_dotfiles,
+            &config.ignore.paths,
+        )?
+    };
+    eprintln!("{} ({} files)", "done".green(), files.len());
+
+    eprint!("  Building code graph... ");
+    let mut graph = CodeGraph::new(repo_path.to_path_buf());
+    for file in &files {
+        let _ = parsers.parse_file(file, &mut graph);
+    }
+    eprintln!("{} ({} nodes)", "done".green(), graph.nodes().count());
+
+    eprint!("  Measuring... ");
+    let metrics = analyzers.measure(&files, Some(&graph), repo_path, config);
+    eprintln!("{} ({} metric(s))", "done".green(), metrics.len());
+    Ok(metrics)
+}
+
+fn print_report(metrics: &[Metric], budget: &Budget) {
+    println!("  {} {}", "Budget:".bold(), budget.describe());
+    if metrics.is_empty() {
+        println!("  {}", "No analyzer has a threshold to calibrate".dimmed());
+        return;
+    }
+    for metric in metrics {
+        print_metric(metric, budget);
+    }
+}
+
+fn print_metric(metric: &Metric, budget: &Budget) {
+    println!("\n  {} — {}", metric.setting.bold(), metric.label);
+    println!("    {}", metric.summary());
+    if metric.values.is_empty() {
+        println!("    {}", "nothing measured".dimmed());
+        return;
+    }
+    let suggested = metric.suggest(budget).map(|(t, _)| t);
+    println!(
+        "    {}  {}",
+        format!("{:>9}", "threshold").dimmed(),
+        format!("{:>8}", "findings").dimmed()
+    );
+    // Rows where the findings change, plus the current and suggested ones
+    let mut previous = None;
+    for &(threshold, findings) in &metric.candidates {
+        let current = (threshold - metric.current).abs() < 1e-9;
+        if previous == Some(findings) && !current && suggested != Some(threshold) {
+            continue;
+        }
+        previous = Some(findings);
+        let mut marks = Vec::new();
+        if current {
+            marks.push("current");
+        }
+        if suggested == Some(threshold) {
+            marks.push("suggested");
+        }
+        let row = format!(
+            "    {:>9}  {:>8}  {}",
+            metric.format(threshold),
+            findings,
+            marks.join(", ")
+        );
+        let row = row.trim_end();
+        if suggested == Some(threshold) {
+            println!("{}", row.green());
+        } else {
+            println!("{}", row);
+        }
+    }
+    if suggested.is_none() {
+        println!(
+            "    {}",
+            format!(
+                "No measured threshold keeps {} within the budget",
+                metric.setting
+            )
+            .yellow()
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! `revet ci` — review the way the CI environment expects
+//!
+//! In GitHub Actions (`GITHUB_ACTIONS=true`) this is a review plus:
+//! - annotations as workflow commands (`--format github`), or — on fork pull
+//!   requests and `pull_request_target` runs, where the token is limited —
+//!   as `--format github-matcher` lines with the bundled problem matcher
+//!   registered around them
+//! - output grouped per file with `::group::`, so the log collapses
+//! - the markdown report appended to the job summary
+//! - with `--upload-sarif`, the SARIF report written with `--output` or
+//!   `--format sarif=PATH` (every chunk of it, in order) uploaded to code
+//!   scanning
+//!
+//! An explicit `--format` wins over the detected style. Outside Actions
+//! `revet ci` is `revet review`.
+//!
+//! Gerrit jobs (the Gerrit Trigger plugin's `GERRIT_*` variables or Zuul's
+//! `ZUUL_*`) are detected for `revet diff --diff-base-detect gerrit`, which
+//! diffs the change under review against its parent patchset (see
+//! [`gerrit_base`]).
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use revet_core::diff::stack::{self, StackChange};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::review;
+use crate::exit::{self, Outcome};
+use crate::output::code_scanning::{self, UploadContext};
+use crate::output::github_matcher::{MATCHER_FILE, MATCHER_JSON, MATCHER_OWNER};
+use crate::output::{resolve_targets, with_output, Format};
+use crate::ProgressiveFormat;
+
+/// How findings become annotations on the run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationStyle {
+    /// `::error file=...::` workflow commands
+    WorkflowCommands,
+    /// Plain lines turned into annotations by a registered problem matcher
+    ProblemMatcher,
+}
+
+impl AnnotationStyle {
+    fn label(self) -> &'static str {
+        match self {
+            AnnotationStyle::WorkflowCommands => "workflow commands",
+            AnnotationStyle::ProblemMatcher => "problem matcher",
+        }
+    }
+
+    fn format(self) -> Format {
+        match self {
+            AnnotationStyle::WorkflowCommands => Format::Github,
+            AnnotationStyle::ProblemMatcher => Format::GithubMatcher,
+        }
+    }
+}
+
+/// The parts of the GitHub Actions environment `revet ci` acts on
+#[derive(Debug, Clone)]
+pub struct ActionsContext {
+    /// `GITHUB_EVENT_NAME`, e.g. `pull_request`
+    pub event_name: String,
+    /// The event is a pull request whose head lives in another repository
+    pub fork: bool,
+    /// `RUNNER_TEMP`, where the problem matcher file is written
+    pub runner_temp: Option<PathBuf>,
+}
+
+impl ActionsContext {
+    /// Read the context from the environment; `None` outside GitHub Actions
+    pub fn from_env() -> Option<Self> {
+        if std::env::var("GITHUB_ACTIONS").ok().as_deref() != Some("true") {
+            return None;
+        }
+        let event_name = std::env::var("GITHUB_EVENT_NAME").unwrap_or_default();
+        let fork = std::env::var_os("GITHUB_EVENT_PATH")
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|payload| serde_json::from_str::<serde_json::Value>(&payload).ok())
+            .is_some_and(|event| is_fork_pull_request(&event));
+        let runner_temp = std::env::var_os("RUNNER_TEMP")
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from);
+        Some(Self {
+            event_name,
+            fork,
+            runner_temp,
+        })
+    }
+
+    /// Problem matcher for `pull_request_target` and fork pull requests,
+    /// workflow commands otherwise
+    pub fn annotation_style(&self) -> AnnotationStyle {
+        if self.event_name == "pull_request_target" || self.fork {
+            AnnotationStyle::ProblemMatcher
+        } else {
+            AnnotationStyle::WorkflowCommands
+        }
+    }
+}
+
+/// Whether an event payload is a pull request from another repository. A
+/// head repository that no longer exists (deleted fork) counts as a fork.
+pub fn is_fork_pull_request(event: &serde_json::Value) -> bool {
+    let Some(pr) = event.get("pull_request") else {
+        return false;
+    };
+    let head = &pr["head"]["repo"];
+    if head.is_null() {
+        return true;
+    }
+    head["fork"].as_bool().unwrap_or(false) || head["full_name"] != pr["base"]["repo"]["full_name"]
+}
+
+pub fn run(
+    path: Option<&Path>,
+    emit_matcher: bool,
+    upload_sarif: bool,
+    cli: &crate::Cli,
+) -> Result<Outcome> {
+    if emit_matcher {
+        print!("{}", MATCHER_JSON);
+        return Ok(Outcome::Success);
+    }
+
+    let upload = if upload_sarif {
+        if cli.output.is_none() && cli.format.iter().all(|f| f.output.is_none()) {
+            return Err(exit::usage(
+                "--upload-sarif uploads the report written with --output <PATH> --format sarif",
+            ));
+        }
+        Some(UploadContext::from_env().ok_or_else(|| {
+            exit::usage(
+                "--upload-sarif needs GITHUB_TOKEN, GITHUB_REPOSITORY, GITHUB_SHA and GITHUB_REF",
+            )
+        })?)
+    } else {
+        None
+    };
+
+    let Some(ctx) = ActionsContext::from_env() else {
+        if upload.is_some() {
+            return Err(exit::usage("--upload-sarif runs in GitHub Actions only"));
+        }
+        eprintln!(
+            "  {}",
+            "Not running in GitHub Actions — running a plain review".dimmed()
+        );
+        return review::run(path, cli);
+    };
+
+    if cli.progressive == Some(ProgressiveFormat::Github) && cli.format.is_empty() {
+        return Err(exit::usage(review::PROGRESSIVE_GITHUB_TWICE));
+    }
+
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+    eprintln!(
+        "{}",
+        format!("  revet v{} — analyzing repository", revet_core::VERSION)
+            .bold()
+            .yellow()
+    );
+    eprintln!();
+
+    let review = review::analyze(&repo_path, cli)?;
+    let style = ctx.annotation_style();
+    let targets = if cli.format.is_empty() {
+        with_output(Vec::new(), style.format(), cli.output.as_deref())?
+    } else {
+        resolve_targets(cli, &review.config)?
+    };
+    let sarif_file = targets
+        .iter()
+        .any(|t| t.format == Format::Sarif && t.output.is_some());
+    if upload.is_some() && !sarif_file {
+        return Err(exit::usage("--upload-sarif needs --format sarif"));
+    }
+    eprintln!(
+        "  {} {} ({} event{})",
+        "Annotations:".bold(),
+        style.label(),
+        if ctx.event_name.is_empty() {
+            "unknown"
+        } else {
+            &ctx.event_name
+        },
+        if ctx.fork { " from a fork" } else { "" }
+    );
+
+    let matcher = targets.iter().any(|t| t.format == Format::GithubMatcher);
+    if matcher {
+        let file = write_matcher(ctx.runner_temp.as_deref())?;
+        println!("::add-matcher::{}", file.display());
+    }
+    let written = review::output(&review, &targets, cli)?;
+    if matcher {
+        println!("::remove-matcher owner={}::", MATCHER_OWNER);
+    }
+    review::write_job_summary(&review, cli);
+    let sarif = written.iter().find(|(format, _)| *format == Format::Sarif);
+    if let (Some(upload), Some((_, written))) = (&upload, sarif) {
+        code_scanning::upload(&written.files, upload)?;
+    }
+
+    Ok(review.outcome(cli))
+}
+
+/// Write the bundled matcher to `dir` (default: the system temp directory)
+fn write_matcher(dir: Option<&Path>) -> Result<PathBuf> {
+    let dir = dir.map_or_else(std::env::temp_dir, Path::to_path_buf);
+    let file = dir.join(MATCHER_FILE);
+    std::fs::write(&file, MATCHER_JSON)
+        .with_context(|| format!("Failed to write {}", file.display()))?;
+    Ok(file)
+}
+
+// ── Gerrit ──────────────────────────────────────────────────────────────────
+
+/// Variables of Gerrit Trigger and Zuul jobs `revet diff` reads
+pub const GERRIT_VARS: &[&str] = &[
+    "GERRIT_PATCHSET_REVISION",
+    "GERRIT_REFSPEC",
+    "GERRIT_BRANCH",
+    "ZUUL_CHANGE",
+    "ZUUL_PATCHSET",
+    "ZUUL_BRANCH",
+];
+
+/// The change a Gerrit Trigger or Zuul job runs for
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GerritContext {
+    /// `GERRIT_PATCHSET_REVISION`: the patchset's commit
+    pub revision: Option<String>,
+    /// `GERRIT_REFSPEC`, or the ref of `ZUUL_CHANGE` / `ZUUL_PATCHSET`,
+    /// e.g. `refs/changes/34/1234/5`
+    pub refspec: Option<String>,
+    /// `GERRIT_BRANCH` / `ZUUL_BRANCH`: the branch the change targets
+    pub branch: Option<String>,
+}
+
+impl GerritContext {
+    /// Read the context from the environment; `None` outside Gerrit jobs
+    pub fn from_env() -> Option<Self> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Read the context from `var`; `None` when it sets none of
+    /// [`GERRIT_VARS`]
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let var = |name: &str| var(name).filter(|v| !v.is_empty());
+        let zuul_ref =
+            var("ZUUL_CHANGE")
+                .zip(var("ZUUL_PATCHSET"))
+                .and_then(|(change, patchset)| {
+                    Some(change_ref(change.parse().ok()?, patchset.parse().ok()?))
+                });
+        let context = Self {
+            revision: var("GERRIT_PATCHSET_REVISION"),
+            refspec: var("GERRIT_REFSPEC").or(zuul_ref),
+            branch: var("GERRIT_BRANCH").or_else(|| var("ZUUL_BRANCH")),
+        };
+        (context != Self::default()).then_some(context)
+    }
+}
+
+/// `refs/changes/34/1234/5` as change 1234, patchset 5
+pub fn parse_change_ref(name: &str) -> Option<(u64, u64)> {
+    let rest = name.strip_prefix("refs/changes/")?;
+    let mut parts = rest.split('/');
+    let (shard, change, patchset) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+    let change: u64 = change.parse().ok()?;
+    let patchset = patchset.parse().ok()?;
+    (shard == format!("{:02}", change % 100)).then_some((change, patchset))
+}
+
+/// The ref Gerrit keeps `patchset` of `change` under
+pub fn change_ref(change: u64, patchset: u64) -> String {
+    format!("refs/changes/{:02}/{}/{}", change % 100, change, patchset)
+}
+
+/// The change and patchset of every fetched `refs/changes/..` ref, by
+/// commit
+pub fn local_change_refs(repo_path: &Path) -> HashMap<String, (u64, u64)> {
+    stack::refs_by_commit(repo_path, "refs/changes/*")
+        .into_iter()
+        .filter_map(|(commit, name)| Some((commit, parse_change_ref(&name)?)))
+        .collect()
+}
+
+/// The change under review and what to diff it against
+#[derive(Debug, Clone)]
+pub struct GerritBase {
+    pub change: StackChange,
+    /// Change number and patchset, when a fetched `refs/changes/..` ref
+    /// points at the change
+    pub patchset: Option<(u64, u64)>,
+    /// `--parent-change`, else the change's first parent: the change below
+    /// it in a stack, or the branch it targets
+    pub base: String,
+}
+
+/// Resolve the change under review — the job's patchset
+/// (`GERRIT_PATCHSET_REVISION`, else its fetched ref), else HEAD — and its
+/// base
+pub fn gerrit_base(
+    repo_path: &Path,
+    context: Option<&GerritContext>,
+    parent_change: Option<&str>,
+) -> Result<GerritBase> {
+    let revision = context.and_then(|c| {
+        [c.revision.as_deref(), c.refspec.as_deref()]
+            .into_iter()
+            .flatten()
+            .find(|rev| StackChange::resolve(repo_path, rev).is_ok())
+    });
+    let change = StackChange::resolve(repo_path, revision.unwrap_or("HEAD"))?;
+    let patchset = local_change_refs(repo_path).get(&change.commit).copied();
+    let base = parent_change.map_or_else(|| change.parent.clone(), str::to_string);
+    Ok(GerritBase {
+        change,
+        patchset,
+        base,
+    })
+}
@@ -2,10 +2,15 @@
 
 use anyhow::Result;
 use colored::Colorize;
-use revet_core::RevetConfig;
+use revet_core::analyzer::registry::{Registry, Source};
+use revet_core::analyzer::rules;
+use revet_core::suppress::PLACEMENT_RULES;
+use revet_core::{AnalyzerDispatcher, RevetConfig};
 use std::path::{Path, PathBuf};
 
-pub fn run(repo_path: &Path) -> Result<()> {
+use crate::exit;
+
+pub fn run(repo_path: &Path, list_rules: bool) -> Result<()> {
     // ── 1. Find config file ──────────────────────────────────────
     let config_path = find_config(repo_path);
 
@@ -24,10 +29,10 @@ pub fn run(repo_path: &Path) -> Result<()> {
             Ok(c) => c,
             Err(e) => {
                 println!();
-                println!("  {} TOML parse error:", "✗".red().bold());
+                println!("  {} Could not load config:", "✗".red().bold());
                 println!("    {}", e.to_string().red());
                 println!();
-                std::process::exit(1);
+                return Err(exit::usage("invalid configuration"));
             }
         },
         None => RevetConfig::default(),
@@ -41,6 +46,10 @@ pub fn run(repo_path: &Path) -> Result<()> {
     print_modules(&config);
     print_custom_rules(&config);
     print_gate(&config);
+    print_policies(&config);
+    if list_rules {
+        print_rule_list(&config);
+    }
 
     // ── 5. Print validation results ──────────────────────────────
     println!();
@@ -51,11 +60,14 @@ pub fn run(repo_path: &Path) -> Result<()> {
         println!("  {} {}", "✗".red().bold(), e);
     }
 
-    if errors.is_empty() {
-        println!("  {} Config is valid.", "✓".green().bold());
-    } else {
-        std::process::exit(1);
+    if !errors.is_empty() {
+        return Err(exit::usage(format!(
+            "{} configuration error{}",
+            errors.len(),
+            if errors.len() == 1 { "" } else { "s" }
+        )));
     }
+    println!("  {} Config is valid.", "✓".green().bold());
 
     Ok(())
 }
@@ -79,8 +91,31 @@ fn print_modules(config: &RevetConfig) {
         ("toolchain", m.toolchain),
         ("hardcoded-endpoints", m.hardcoded_endpoints),
         ("magic-numbers", m.magic_numbers),
+        ("resource-leaks", m.resource_leaks),
+        ("kubernetes", m.kubernetes),
+        ("skew", m.skew),
+        ("hotspots", m.hotspots),
+        ("ownership", m.ownership),
+        ("refactor", m.refactor),
+        ("i18n", m.i18n),
+        ("feature-flags", m.feature_flags),
+        ("debug-artifacts", m.debug_artifacts),
+        ("test-quality", m.test_quality),
+        ("copy", m.copy),
+        ("numeric", m.numeric),
+        ("pagination", m.pagination),
+        ("a11y", m.a11y),
+        ("websec", m.websec),
+        ("contract", m.contract),
+        ("repository", m.repository),
+        ("upgrade", m.upgrade),
         ("test-coverage", m.test_coverage),
+        ("coupling", m.coupling),
+        ("docs", m.docs),
+        ("concurrency", m.concurrency),
         ("duplication", m.duplication),
+        ("suppressions", m.suppressions),
+        ("config-drift", m.config_drift),
     ];
 
     let on: Vec<&str> = modules
@@ -117,6 +152,10 @@ fn print_custom_rules(config: &RevetConfig) {
             .as_deref()
             .map(|id| id.to_string())
             .unwrap_or_else(|| format!("[{}]", i));
+        let label = match &rule.prefix {
+            Some(prefix) => format!("{} {}", label, format!("({}-)", prefix).cyan()),
+            None => label,
+        };
         let paths = if rule.paths.is_empty() {
             "all files".dimmed().to_string()
         } else {
@@ -132,6 +171,66 @@ fn print_custom_rules(config: &RevetConfig) {
     }
 }
 
+/// Every claimed finding prefix, then every module's sub-rules as
+/// `module:rule`; custom ones are marked with their stable ID
+fn print_rule_list(config: &RevetConfig) {
+    // Conflicts are reported with the validation errors
+    let registry = Registry::from_config(config).unwrap_or_default();
+    println!();
+    println!("  {}", "Finding prefixes".bold());
+    for (prefix, claim) in registry.prefixes() {
+        let source = match claim.source {
+            Source::Builtin => claim.source.to_string().dimmed(),
+            _ => claim.source.to_string().cyan(),
+        };
+        println!(
+            "    {} {:<9} {} {}",
+            "·".dimmed(),
+            prefix,
+            claim.claimant,
+            source
+        );
+    }
+
+    let mut catalog = rules::catalog();
+    for (module, sub_rules) in AnalyzerDispatcher::new_with_config(config).rules() {
+        let listed = catalog.entry(module).or_default();
+        for rule in sub_rules {
+            if !listed.contains(rule) {
+                listed.push(rule);
+            }
+        }
+    }
+
+    println!();
+    println!("  {}", "Sub-rules".bold());
+    for (module, sub_rules) in catalog {
+        if sub_rules.is_empty() {
+            continue;
+        }
+        println!("    {}", module.bold());
+        for rule in sub_rules {
+            if rule.starts_with("custom-") {
+                println!(
+                    "      {} {}:{} {}",
+                    "·".dimmed(),
+                    module,
+                    rule,
+                    format!("(custom, SEC-{})", rule).cyan()
+                );
+            } else {
+                println!("      {} {}:{}", "·".dimmed(), module, rule);
+            }
+        }
+    }
+
+    println!();
+    println!("  {}", "Suppression placement".bold());
+    for (form, covers) in PLACEMENT_RULES {
+        println!("    {} {:<27} {}", "·".dimmed(), form, covers.dimmed());
+    }
+}
+
 fn print_gate(config: &RevetConfig) {
     let g = &config.gate;
     if g.is_empty() {
@@ -151,6 +250,27 @@ fn print_gate(config: &RevetConfig) {
     println!("  {} {}", "Gate:".bold(), parts.join(", ").yellow());
 }
 
+fn print_policies(config: &RevetConfig) {
+    if config.policies.is_empty() {
+        return;
+    }
+    println!();
+    println!(
+        "  {} {} policy(ies)",
+        "Policies:".bold(),
+        config.policies.len()
+    );
+    for p in &config.policies {
+        println!(
+            "    {} {} ({}) {}",
+            "·".dimmed(),
+            p.name.bold(),
+            p.action,
+            p.filter.dimmed()
+        );
+    }
+}
+
 fn find_config(start: &Path) -> Option<PathBuf> {
     let mut current = start;
     loop {
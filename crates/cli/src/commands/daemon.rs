@@ -0,0 +1,891 @@
+//! `revet daemon` — keep revet resident for one repository and serve
+//! `review` and `diff` over a Unix domain socket
+//!
+//! `start` runs `revet daemon run` in the background. It listens on
+//! [`DAEMON_SOCKET`] in the repository's local directory (see
+//! [`StoragePaths::local_dir`]), which only the owning user can open. A
+//! `revet review` or `revet diff` of the repository finds the socket, sends
+//! its arguments, environment and working directory, prints the output the
+//! daemon streams back and exits with the daemon's exit code. Without a live
+//! daemon, with [`NO_DAEMON_ENV`] set, or when the daemon rejects the
+//! request (another protocol or revet version, a path outside its
+//! repository), the command runs in process as usual.
+//!
+//! Each message is one frame: a 4-byte big-endian length and that many bytes
+//! of JSON, a [`Request`] from the client and [`Reply`] frames back.
+//!
+//! Runs are serialized and executed by a worker process, `revet daemon
+//! worker`, which the daemon starts with the client's environment, working
+//! directory and colour choice. Its stdout and stderr are pipes the daemon
+//! sends over the socket; the daemon never changes its own environment,
+//! working directory or descriptors, so `status` requests are answered while
+//! a run is in progress. The worker keeps parsed files in memory between
+//! runs (see [`FileGraphCache::keep_resident`]), and analyzer findings per
+//! file content (see [`AnalysisMemo`](revet_core::AnalysisMemo)), sized by
+//! `[performance] memo_mb`. An edit to `.revet.toml` or the baseline, or a
+//! run with another environment, working directory or colour choice,
+//! replaces the worker. The daemon exits after `--idle-timeout` without
+//! requests, or on `revet daemon stop`.
+//!
+//! [`FileGraphCache::keep_resident`]: revet_core::FileGraphCache::keep_resident
+
+use anyhow::{anyhow, bail, Context, Result};
+use colored::Colorize;
+use revet_core::storage::DAEMON_SOCKET;
+use revet_core::{MemoStats, StoragePaths};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::{Cli, Commands};
+
+/// Version of the socket protocol; a daemon rejects requests of another
+pub const PROTOCOL: u32 = 1;
+
+/// Set to run `review` and `diff` in process even when a daemon is running
+pub const NO_DAEMON_ENV: &str = "REVET_NO_DAEMON";
+
+/// Default `--idle-timeout`
+pub const DEFAULT_IDLE_TIMEOUT: &str = "30m";
+
+/// Largest frame either side accepts
+const MAX_FRAME: usize = 64 << 20;
+
+/// What a client asks the daemon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Request {
+    pub protocol: u32,
+    /// revet version of the client
+    pub version: String,
+    #[serde(flatten)]
+    pub kind: RequestKind,
+}
+
+impl Request {
+    pub fn new(kind: RequestKind) -> Self {
+        Self {
+            protocol: PROTOCOL,
+            version: revet_core::VERSION.to_string(),
+            kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "request", rename_all = "snake_case")]
+pub enum RequestKind {
+    /// Run a command line (`revet review ...`, `revet diff ...`)
+    Run {
+        /// The client's working directory
+        cwd: PathBuf,
+        /// Command line, program name first
+        args: Vec<String>,
+        /// The client's environment
+        env: Vec<(String, String)>,
+        /// Whether the client's output is coloured
+        color: bool,
+    },
+    Status,
+    Stop,
+}
+
+/// What the daemon sends back, in order: output chunks then the exit, or a
+/// single rejection, status or stop acknowledgement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "reply", rename_all = "snake_case")]
+pub enum Reply {
+    /// Not served; the client runs the command itself
+    Rejected {
+        reason: String,
+    },
+    Stdout {
+        data: String,
+    },
+    Stderr {
+        data: String,
+    },
+    /// The command finished: exit code and `--print-exit-reason` line
+    Exit {
+        code: i32,
+        reason: String,
+    },
+    Status(DaemonStatus),
+    Stopping,
+}
+
+/// `revet daemon status`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    pub pid: u32,
+    pub repo: PathBuf,
+    pub version: String,
+    /// Seconds since the daemon started
+    pub uptime_secs: u64,
+    /// Runs served
+    pub requests: usize,
+    /// Whether a run is in progress
+    pub busy: bool,
+    /// Files whose parse is held in memory
+    pub resident_files: usize,
+    /// Times a config or baseline edit dropped the resident state
+    pub invalidations: usize,
+    pub idle_timeout_secs: u64,
+    /// What the analyzer memo did since the daemon started; `None` with
+    /// `[performance] memo_mb = 0`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<MemoStats>,
+}
+
+/// Write `message` as one frame
+pub fn write_frame(writer: &mut impl Write, message: &impl Serialize) -> Result<()> {
+    let json = serde_json::to_vec(message)?;
+    writer.write_all(&(json.len() as u32).to_be_bytes())?;
+    writer.write_all(&json)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read one frame; `None` when the other side closed the connection
+pub fn read_frame<T: DeserializeOwned>(reader: &mut impl Read) -> Result<Option<T>> {
+    let mut len = [0u8; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_FRAME {
+        bail!(
+            "frame of {} bytes is over the {} byte limit",
+            len,
+            MAX_FRAME
+        );
+    }
+    let mut json = vec![0u8; len];
+    reader.read_exact(&mut json)?;
+    Ok(Some(serde_json::from_slice(&json)?))
+}
+
+fn repo_path(path: Option<&Path>) -> PathBuf {
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf())
+}
+
+/// The socket of the daemon for `repo`; `None` without a local directory
+pub fn socket_path(repo: &Path) -> Option<PathBuf> {
+    Some(
+        StoragePaths::discover(repo)
+            .local_dir()?
+            .join(DAEMON_SOCKET),
+    )
+}
+
+/// Repository a `review` or `diff` command line works on, relative to the
+/// working directory; `None` for commands the daemon doesn't serve
+fn served_target(cli: &Cli) -> Option<&Path> {
+    match &cli.command {
+        None => Some(Path::new(".")),
+        // The daemon has no stdin of the client to read
+        Some(Commands::Review { stdin: true, .. }) => None,
+        Some(Commands::Review { path, .. }) => Some(path.as_deref().unwrap_or(Path::new("."))),
+        Some(Commands::Diff { .. }) => Some(Path::new(".")),
+        _ => None,
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match (secs / 3600, secs % 3600 / 60) {
+        (0, 0) => format!("{}s", secs),
+        (0, minutes) => format!("{}m", minutes),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+/// Ask the daemon of `repo` for its status; `None` when none answers
+pub fn query_status(repo: &Path) -> Option<DaemonStatus> {
+    match exchange(repo, RequestKind::Status)? {
+        Reply::Status(status) => Some(status),
+        _ => None,
+    }
+}
+
+pub fn status(path: Option<&Path>) -> Result<Option<DaemonStatus>> {
+    let repo = repo_path(path);
+    let Some(status) = query_status(&repo) else {
+        println!(
+            "  {}",
+            format!("No daemon running for {}", repo.display()).dimmed()
+        );
+        return Ok(None);
+    };
+    println!(
+        "  {} pid {} · {} · up {} · {} run(s){} · {} file(s) resident · idle timeout {}",
+        "running".green(),
+        status.pid,
+        status.repo.display(),
+        format_duration(Duration::from_secs(status.uptime_secs)),
+        status.requests,
+        if status.busy {
+            " (one in progress)"
+        } else {
+            ""
+        },
+        status.resident_files,
+        format_duration(Duration::from_secs(status.idle_timeout_secs)),
+    );
+    if let Some(memo) = &status.memo {
+        println!(
+            "  {}",
+            format!(
+                "Memo: {} entries ({:.1} MB) · {:.0}% hits · {} evicted · {} invalidated",
+                memo.entries,
+                memo.bytes as f64 / (1024.0 * 1024.0),
+                memo.hit_rate() * 100.0,
+                memo.evictions,
+                memo.invalidations
+            )
+            .dimmed()
+        );
+    }
+    if status.invalidations > 0 {
+        println!(
+            "  {}",
+            format!(
+                "Resident state dropped {} time(s) after config or baseline edits",
+                status.invalidations
+            )
+            .dimmed()
+        );
+    }
+    Ok(Some(status))
+}
+
+pub fn stop(path: Option<&Path>) -> Result<bool> {
+    let repo = repo_path(path);
+    let stopped = matches!(exchange(&repo, RequestKind::Stop), Some(Reply::Stopping));
+    if stopped {
+        eprintln!("  {} daemon for {}", "stopped".green(), repo.display());
+    } else {
+        eprintln!(
+            "  {}",
+            format!("No daemon running for {}", repo.display()).dimmed()
+        );
+    }
+    Ok(stopped)
+}
+
+#[cfg(not(unix))]
+fn exchange(_repo: &Path, _kind: RequestKind) -> Option<Reply> {
+    None
+}
+
+#[cfg(not(unix))]
+pub fn start(_path: Option<&Path>, _idle_timeout: Duration) -> Result<()> {
+    Err(crate::exit::usage(
+        "revet daemon needs Unix domain sockets, which this platform lacks",
+    ))
+}
+
+#[cfg(not(unix))]
+pub fn run(_path: Option<&Path>, _idle_timeout: Duration) -> Result<()> {
+    start(None, Duration::ZERO)
+}
+
+#[cfg(not(unix))]
+pub fn work(_path: Option<&Path>, _color: bool, _execute: Execute) -> Result<()> {
+    start(None, Duration::ZERO)
+}
+
+#[cfg(not(unix))]
+pub fn proxy(_cli: &Cli) -> Option<(i32, String)> {
+    None
+}
+
+/// How the worker runs a command line: the exit code and the
+/// `--print-exit-reason` line, as `main` does
+pub type Execute = fn(&Cli) -> (i32, String);
+
+#[cfg(unix)]
+pub use unix::{proxy, run, start, work};
+
+#[cfg(unix)]
+use unix::exchange;
+
+#[cfg(unix)]
+mod unix {
+    use super::*;
+    use revet_core::{AnalysisMemo, FileGraphCache, RevetConfig};
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::os::unix::process::CommandExt;
+    use std::process::{Child, ChildStdin, Stdio};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::mpsc::{self, Receiver, Sender};
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    /// Send `kind` to the daemon of `repo` and read its single reply
+    pub(super) fn exchange(repo: &Path, kind: RequestKind) -> Option<Reply> {
+        let mut stream = UnixStream::connect(socket_path(repo)?).ok()?;
+        write_frame(&mut stream, &Request::new(kind)).ok()?;
+        read_frame(&mut stream).ok()?
+    }
+
+    pub fn start(path: Option<&Path>, idle_timeout: Duration) -> Result<()> {
+        let repo = repo_path(path);
+        if let Some(status) = query_status(&repo) {
+            eprintln!(
+                "  {}",
+                format!("Daemon already running (pid {})", status.pid).dimmed()
+            );
+            return Ok(());
+        }
+        let socket = socket_path(&repo)
+            .ok_or_else(|| anyhow!("No local directory for the daemon socket — set HOME"))?;
+        let dir = socket.parent().unwrap_or(Path::new("."));
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let log = std::fs::File::create(dir.join("daemon.log"))?;
+
+        let child = std::process::Command::new(std::env::current_exe()?)
+            .arg("daemon")
+            .arg("run")
+            .arg(&repo)
+            .arg("--idle-timeout")
+            .arg(format!("{}s", idle_timeout.as_secs()))
+            .env_remove(NO_DAEMON_ENV)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(log)
+            // Out of the terminal's process group, so Ctrl-C in the shell
+            // that started it doesn't stop it
+            .process_group(0)
+            .spawn()
+            .context("Failed to start the daemon")?;
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while Instant::now() < deadline {
+            if let Some(status) = query_status(&repo) {
+                eprintln!(
+                    "  {} daemon for {} (pid {}, idle timeout {})",
+                    "started".green(),
+                    repo.display(),
+                    status.pid,
+                    format_duration(idle_timeout)
+                );
+                return Ok(());
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        bail!(
+            "Daemon (pid {}) did not answer on {} — see {}",
+            child.id(),
+            socket.display(),
+            dir.join("daemon.log").display()
+        )
+    }
+
+    /// What the worker reports after a run, ending its stdout for the run
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct WorkerExit {
+        code: i32,
+        reason: String,
+        /// Files whose parse the worker holds in memory
+        resident_files: usize,
+        memo: Option<MemoStats>,
+    }
+
+    /// Ends the worker's output of one run on stdout and stderr, around the
+    /// [`WorkerExit`] JSON on stdout; text output never holds it
+    const END: u8 = 0;
+
+    /// State shared by the connections of a daemon
+    struct Daemon {
+        repo: PathBuf,
+        started: Instant,
+        idle_timeout: Duration,
+        /// The worker running requests; held for the length of a run
+        worker: Mutex<Option<Worker>>,
+        /// Set when a config or baseline edit outdated the worker's state
+        stale: AtomicBool,
+        busy: AtomicBool,
+        requests: AtomicUsize,
+        invalidations: AtomicUsize,
+        /// What the worker held after its last run
+        resident_files: AtomicUsize,
+        memo: Mutex<Option<MemoStats>>,
+        last_active: Mutex<Instant>,
+        stop: AtomicBool,
+    }
+
+    impl Daemon {
+        fn touch(&self) {
+            *self.last_active.lock().unwrap() = Instant::now();
+        }
+
+        fn is_idle(&self) -> bool {
+            !self.busy.load(Ordering::SeqCst)
+                && self.last_active.lock().unwrap().elapsed() >= self.idle_timeout
+        }
+
+        fn status(&self) -> DaemonStatus {
+            DaemonStatus {
+                pid: std::process::id(),
+                repo: self.repo.clone(),
+                version: revet_core::VERSION.to_string(),
+                uptime_secs: self.started.elapsed().as_secs(),
+                requests: self.requests.load(Ordering::SeqCst),
+                busy: self.busy.load(Ordering::SeqCst),
+                resident_files: self.resident_files.load(Ordering::SeqCst),
+                invalidations: self.invalidations.load(Ordering::SeqCst),
+                idle_timeout_secs: self.idle_timeout.as_secs(),
+                memo: *self.memo.lock().unwrap(),
+            }
+        }
+    }
+
+    /// What a worker was started with; a run needing another gets a new one
+    #[derive(PartialEq, Eq)]
+    struct WorkerKey {
+        cwd: PathBuf,
+        env: Vec<(String, String)>,
+        color: bool,
+    }
+
+    /// A `revet daemon worker` process and the threads forwarding its output
+    struct Worker {
+        key: WorkerKey,
+        child: Child,
+        stdin: ChildStdin,
+        /// One message per output stream at the end of a run: the text
+        /// between the [`END`] bytes, or `None` when the stream closed
+        ends: Receiver<Option<String>>,
+        /// The client output is sent to during a run
+        client: Arc<Mutex<Option<UnixStream>>>,
+    }
+
+    impl Worker {
+        fn spawn(repo: &Path, key: WorkerKey) -> Result<Self> {
+            let mut command = std::process::Command::new(std::env::current_exe()?);
+            command
+                .arg("daemon")
+                .arg("worker")
+                .arg(repo)
+                .env_clear()
+                .envs(key.env.iter().map(|(k, v)| (k, v)))
+                .current_dir(&key.cwd)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+            if key.color {
+                command.arg("--color");
+            }
+            let mut child = command.spawn().context("Failed to start a worker")?;
+            let stdin = child.stdin.take().context("worker stdin")?;
+            let stdout = child.stdout.take().context("worker stdout")?;
+            let stderr = child.stderr.take().context("worker stderr")?;
+
+            let client = Arc::new(Mutex::new(None));
+            let (sender, ends) = mpsc::channel();
+            {
+                let (client, sender) = (Arc::clone(&client), sender.clone());
+                std::thread::spawn(move || forward(stdout, true, &client, &sender));
+            }
+            {
+                let (client, sender) = (Arc::clone(&client), sender);
+                std::thread::spawn(move || forward(stderr, false, &client, &sender));
+            }
+            Ok(Self {
+                key,
+                child,
+                stdin,
+                ends,
+                client,
+            })
+        }
+
+        /// Run `args` with its output sent to `stream`
+        fn run(&mut self, args: &[String], stream: &UnixStream) -> Result<WorkerExit> {
+            *self.client.lock().unwrap() = Some(stream.try_clone()?);
+            let result = self.exchange(args);
+            *self.client.lock().unwrap() = None;
+            result
+        }
+
+        fn exchange(&mut self, args: &[String]) -> Result<WorkerExit> {
+            write_frame(&mut self.stdin, &args)?;
+            let mut exit = None;
+            for _ in 0..2 {
+                match self.ends.recv() {
+                    Ok(Some(end)) if end.is_empty() => {}
+                    Ok(Some(end)) => exit = Some(serde_json::from_str(&end)?),
+                    _ => bail!("the worker exited mid-run"),
+                }
+            }
+            exit.ok_or_else(|| anyhow!("the worker sent no exit status"))
+        }
+    }
+
+    impl Drop for Worker {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+
+    /// Serve `path` in the foreground until stopped or idle (`revet daemon run`)
+    pub fn run(path: Option<&Path>, idle_timeout: Duration) -> Result<()> {
+        let repo = repo_path(path);
+        let socket = socket_path(&repo)
+            .ok_or_else(|| anyhow!("No local directory for the daemon socket — set HOME"))?;
+        let dir = socket.parent().unwrap_or(Path::new("."));
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+        if UnixStream::connect(&socket).is_ok() {
+            bail!("A daemon is already listening on {}", socket.display());
+        }
+        let _ = std::fs::remove_file(&socket);
+        let listener = UnixListener::bind(&socket)
+            .with_context(|| format!("Failed to listen on {}", socket.display()))?;
+        std::fs::set_permissions(&socket, std::fs::Permissions::from_mode(0o600))?;
+        listener.set_nonblocking(true)?;
+
+        let daemon = Arc::new(Daemon {
+            repo: repo.clone(),
+            started: Instant::now(),
+            idle_timeout,
+            worker: Mutex::new(None),
+            stale: AtomicBool::new(false),
+            busy: AtomicBool::new(false),
+            requests: AtomicUsize::new(0),
+            invalidations: AtomicUsize::new(0),
+            resident_files: AtomicUsize::new(0),
+            memo: Mutex::new(None),
+            last_active: Mutex::new(Instant::now()),
+            stop: AtomicBool::new(false),
+        });
+        let _watcher = watch_state(&daemon);
+        eprintln!(
+            "revet daemon {} serving {} on {}",
+            std::process::id(),
+            repo.display(),
+            socket.display()
+        );
+
+        while !daemon.stop.load(Ordering::SeqCst) && !daemon.is_idle() {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    daemon.touch();
+                    let daemon = Arc::clone(&daemon);
+                    std::thread::spawn(move || {
+                        if let Err(e) = serve(stream, &daemon) {
+                            eprintln!("request failed: {:#}", e);
+                        }
+                        daemon.touch();
+                    });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+                Err(e) => eprintln!("accept failed: {}", e),
+            }
+        }
+        // Let a run in progress finish before the socket goes
+        drop(
+            daemon
+                .worker
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .take(),
+        );
+        let _ = std::fs::remove_file(&socket);
+        Ok(())
+    }
+
+    /// Watch `.revet.toml` and the baseline, replacing the worker and its
+    /// resident state when either changes
+    fn watch_state(daemon: &Arc<Daemon>) -> Option<notify::RecommendedWatcher> {
+        use notify::{RecursiveMode, Watcher};
+        let paths = StoragePaths::discover(&daemon.repo);
+        let baseline = paths.baseline_path().to_path_buf();
+        let config = daemon.repo.join(".revet.toml");
+        let watched = [config.clone(), baseline.clone()];
+        let state = Arc::clone(daemon);
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let Ok(event) = event else {
+                    return;
+                };
+                if event.paths.iter().any(|p| watched.contains(p)) {
+                    // The next run starts a new worker
+                    state.stale.store(true, Ordering::SeqCst);
+                    state.resident_files.store(0, Ordering::SeqCst);
+                    *state.memo.lock().unwrap() = None;
+                    state.invalidations.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+            .ok()?;
+        let mut dirs: Vec<&Path> = [config.parent(), baseline.parent()]
+            .into_iter()
+            .flatten()
+            .collect();
+        dirs.dedup();
+        for dir in dirs {
+            let _ = watcher.watch(dir, RecursiveMode::NonRecursive);
+        }
+        Some(watcher)
+    }
+
+    /// Answer one connection
+    fn serve(mut stream: UnixStream, daemon: &Daemon) -> Result<()> {
+        stream.set_nonblocking(false)?;
+        let Some(request) = read_frame::<Request>(&mut stream)? else {
+            return Ok(());
+        };
+        if request.protocol != PROTOCOL || request.version != revet_core::VERSION {
+            let reason = format!(
+                "daemon speaks protocol {} of revet {}, not {} of {}",
+                PROTOCOL,
+                revet_core::VERSION,
+                request.protocol,
+                request.version
+            );
+            return write_frame(&mut stream, &Reply::Rejected { reason });
+        }
+        match request.kind {
+            RequestKind::Status => write_frame(&mut stream, &Reply::Status(daemon.status())),
+            RequestKind::Stop => {
+                daemon.stop.store(true, Ordering::SeqCst);
+                write_frame(&mut stream, &Reply::Stopping)
+            }
+            RequestKind::Run {
+                cwd,
+                args,
+                env,
+                color,
+            } => {
+                if let Err(reason) = admit(daemon, &cwd, &args) {
+                    return write_frame(&mut stream, &Reply::Rejected { reason });
+                }
+                let mut worker = daemon.worker.lock().unwrap_or_else(|e| e.into_inner());
+                daemon.busy.store(true, Ordering::SeqCst);
+                let key = WorkerKey { cwd, env, color };
+                let result = run_in_worker(daemon, &mut worker, key, &args, &stream);
+                daemon.requests.fetch_add(1, Ordering::SeqCst);
+                daemon.busy.store(false, Ordering::SeqCst);
+                let exit = result?;
+                daemon
+                    .resident_files
+                    .store(exit.resident_files, Ordering::SeqCst);
+                *daemon.memo.lock().unwrap() = exit.memo;
+                write_frame(
+                    &mut stream,
+                    &Reply::Exit {
+                        code: exit.code,
+                        reason: exit.reason,
+                    },
+                )
+            }
+        }
+    }
+
+    /// Why the daemon doesn't serve a run request, if it doesn't
+    fn admit(daemon: &Daemon, cwd: &Path, args: &[String]) -> std::result::Result<(), String> {
+        use clap::Parser;
+        let cli = Cli::try_parse_from(args).map_err(|e| e.to_string())?;
+        let target = served_target(&cli).ok_or("not a review or diff command line")?;
+        let target = std::fs::canonicalize(cwd.join(target))
+            .map_err(|e| format!("{}: {}", cwd.join(target).display(), e))?;
+        if !target.starts_with(&daemon.repo) {
+            return Err(format!(
+                "{} is outside the daemon's repository {}",
+                target.display(),
+                daemon.repo.display()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Run `args` in `worker`, first starting one for `key` when there is
+    /// none, it was started for another key or its state is outdated. A
+    /// worker that fails mid-run is dropped.
+    fn run_in_worker(
+        daemon: &Daemon,
+        worker: &mut Option<Worker>,
+        key: WorkerKey,
+        args: &[String],
+        stream: &UnixStream,
+    ) -> Result<WorkerExit> {
+        let stale = daemon.stale.swap(false, Ordering::SeqCst);
+        if stale || worker.as_ref().is_some_and(|w| w.key != key) {
+            *worker = None;
+        }
+        let current = match worker {
+            Some(current) => current,
+            None => worker.insert(Worker::spawn(&daemon.repo, key)?),
+        };
+        let result = current.run(args, stream);
+        if result.is_err() {
+            *worker = None;
+        }
+        result
+    }
+
+    /// Serve the requests the daemon writes to stdin, one at a time, ending
+    /// each run's output as [`WorkerExit`] describes (`revet daemon worker`)
+    pub fn work(path: Option<&Path>, color: bool, execute: Execute) -> Result<()> {
+        use clap::Parser;
+        colored::control::set_override(color);
+        let repo = repo_path(path);
+        FileGraphCache::keep_resident();
+        AnalysisMemo::start_session(&RevetConfig::find_and_load(&repo).unwrap_or_default());
+
+        let mut stdin = std::io::stdin().lock();
+        while let Some(args) = read_frame::<Vec<String>>(&mut stdin)? {
+            // Admitted by the daemon, so it parses
+            let cli = Cli::try_parse_from(&args)?;
+            super::super::set_command_line(Some(args));
+            let (code, reason) = execute(&cli);
+            super::super::set_command_line(None);
+            let exit = WorkerExit {
+                code,
+                reason,
+                resident_files: FileGraphCache::resident_count(),
+                memo: AnalysisMemo::session().map(|memo| memo.stats()),
+            };
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(&[END])?;
+            stdout.write_all(serde_json::to_string(&exit)?.as_bytes())?;
+            stdout.write_all(&[END])?;
+            stdout.flush()?;
+            let mut stderr = std::io::stderr().lock();
+            stderr.write_all(&[END, END])?;
+            stderr.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Send what `reader` yields as output frames to the client being
+    /// served until it closes, holding back a character split across reads,
+    /// and report each run's end to `ends`
+    fn forward(
+        mut reader: impl Read,
+        stdout: bool,
+        client: &Mutex<Option<UnixStream>>,
+        ends: &Sender<Option<String>>,
+    ) {
+        let send = |data: String| {
+            if data.is_empty() {
+                return;
+            }
+            let reply = if stdout {
+                Reply::Stdout { data }
+            } else {
+                Reply::Stderr { data }
+            };
+            if let Some(stream) = client.lock().unwrap().as_mut() {
+                let _ = write_frame(stream, &reply);
+            }
+        };
+        let mut buffer = [0u8; 8192];
+        let mut pending: Vec<u8> = Vec::new();
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            pending.extend_from_slice(&buffer[..read]);
+            loop {
+                let Some(start) = pending.iter().position(|&b| b == END) else {
+                    let valid = match std::str::from_utf8(&pending) {
+                        Ok(_) => pending.len(),
+                        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                        Err(_) => pending.len(),
+                    };
+                    send(String::from_utf8_lossy(&pending[..valid]).into_owned());
+                    pending.drain(..valid);
+                    break;
+                };
+                send(String::from_utf8_lossy(&pending[..start]).into_owned());
+                pending.drain(..start);
+                // The end's closing byte may not have arrived yet
+                let Some(len) = pending[1..].iter().position(|&b| b == END) else {
+                    break;
+                };
+                let end = String::from_utf8_lossy(&pending[1..=len]).into_owned();
+                pending.drain(..len + 2);
+                let _ = ends.send(Some(end));
+            }
+        }
+        send(String::from_utf8_lossy(&pending).into_owned());
+        let _ = ends.send(None);
+    }
+
+    /// Run `cli` through the daemon of its repository, if one is live:
+    /// the exit code and `--print-exit-reason` line. `None` when the
+    /// command should run in process.
+    pub fn proxy(cli: &Cli) -> Option<(i32, String)> {
+        if std::env::var_os(NO_DAEMON_ENV).is_some_and(|v| !v.is_empty()) {
+            return None;
+        }
+        let target = served_target(cli)?;
+        let cwd = std::env::current_dir().ok()?;
+        let repo = std::fs::canonicalize(cwd.join(target)).ok()?;
+        let socket = socket_path(&repo)?;
+        if !socket.exists() {
+            return None;
+        }
+        let mut stream = UnixStream::connect(&socket).ok()?;
+        let request = Request::new(RequestKind::Run {
+            cwd,
+            args: std::env::args_os()
+                .map(|a| a.to_string_lossy().into_owned())
+                .collect(),
+            env: std::env::vars_os()
+                .map(|(k, v)| {
+                    (
+                        k.to_string_lossy().into_owned(),
+                        v.to_string_lossy().into_owned(),
+                    )
+                })
+                .collect(),
+            color: colored::control::SHOULD_COLORIZE.should_colorize(),
+        });
+        write_frame(&mut stream, &request).ok()?;
+
+        let mut printed = false;
+        loop {
+            let reply = match read_frame::<Reply>(&mut stream) {
+                Ok(Some(reply)) => reply,
+                // Nothing shown yet: the command can still run in process
+                _ if !printed => return None,
+                _ => {
+                    eprintln!("Error: the daemon closed the connection mid-run");
+                    let kind = crate::exit::ErrorKind::Internal;
+                    return Some((kind.code(), kind.reason()));
+                }
+            };
+            match reply {
+                Reply::Stdout { data } => {
+                    printed = true;
+                    print!("{}", data);
+                    let _ = std::io::stdout().flush();
+                }
+                Reply::Stderr { data } => {
+                    printed = true;
+                    eprint!("{}", data);
+                }
+                Reply::Exit { code, reason } => return Some((code, reason)),
+                Reply::Rejected { .. } if !printed => return None,
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! `revet debug capture` / `revet debug replay` — bundle one finding into a
+//! `.revetcase` archive and re-run its analyzer alone on it
+
+use anyhow::{anyhow, bail, Context, Result};
+use colored::Colorize;
+use revet_core::case::{self, CaptureOptions, Case, CaseTarget, Replay};
+use revet_core::{RevetConfig, Severity, StoragePaths};
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use crate::exit;
+use crate::run_log;
+use crate::OutputFormat;
+
+/// Arguments of `revet debug capture`
+pub struct CaptureArgs<'a> {
+    pub finding: &'a str,
+    pub output: Option<&'a Path>,
+    pub run: Option<&'a str>,
+    /// Lines of context to keep when redacting; `None` bundles the file whole
+    pub redact: Option<usize>,
+    /// Accept the redacted content without asking
+    pub yes: bool,
+}
+
+/// Capture a finding of a recorded run as a case. Returns the path written.
+pub fn capture(path: Option<&Path>, args: &CaptureArgs) -> Result<PathBuf> {
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+    let config = RevetConfig::find_and_load(&repo_path)?;
+    let paths = StoragePaths::resolve(&repo_path, &config);
+
+    let run_id = match args.run {
+        Some(id) => id.to_string(),
+        None => run_log::latest_run_id(&paths)
+            .ok_or_else(|| anyhow!("No run logs found. Run `revet review` first."))?,
+    };
+    let log = run_log::load_run_log(&paths, &run_id)?;
+    let recorded = log
+        .findings
+        .iter()
+        .find(|f| f.id.eq_ignore_ascii_case(args.finding))
+        .ok_or_else(|| anyhow!("Finding {} is not in run {}", args.finding, run_id))?;
+    let id = recorded.id.clone();
+
+    let target = CaseTarget {
+        id: recorded.id.clone(),
+        file: PathBuf::from(&recorded.file),
+        line: recorded.line,
+        severity: match recorded.severity.as_str() {
+            "error" => Severity::Error,
+            "warning" => Severity::Warning,
+            _ => Severity::Info,
+        },
+        rule: recorded.rule.clone(),
+        message: recorded.message.clone(),
+    };
+    let options = CaptureOptions {
+        redact: args.redact,
+    };
+    let case = case::capture(&target, &repo_path, &config, &paths, &options)?;
+
+    if case.manifest.redaction.is_some() && !args.yes {
+        preview(&case);
+        if !confirm()? {
+            bail!("Capture cancelled — nothing written");
+        }
+    }
+
+    let output = match args.output {
+        Some(output) => output.to_path_buf(),
+        None => PathBuf::from(format!("{}.{}", id, case::EXTENSION)),
+    };
+    case.write(&output)?;
+
+    eprintln!(
+        "  {} {} ({} · {}) to {}",
+        "captured".green(),
+        id,
+        case.manifest.file,
+        case.manifest.analyzer.name,
+        output.display()
+    );
+    if case.graph.is_some() {
+        eprintln!(
+            "  {}",
+            "includes the file's graph neighbourhood from the cache".dimmed()
+        );
+    }
+    Ok(output)
+}
+
+/// Print the lines a redacted case keeps
+fn preview(case: &Case) {
+    let Some(redaction) = &case.manifest.redaction else {
+        return;
+    };
+    eprintln!(
+        "  {} {} keeps these lines (everything else is blanked):",
+        "redacted".yellow(),
+        case.manifest.file
+    );
+    let lines: Vec<&str> = case.source.lines().collect();
+    for &(start, end) in &redaction.kept {
+        for number in start..=end {
+            let text = lines.get(number - 1).copied().unwrap_or("");
+            let masked = if redaction.masked.contains(&number) {
+                " (secret masked)".yellow().to_string()
+            } else {
+                String::new()
+            };
+            eprintln!("  {:>5} │ {}{}", number.to_string().dimmed(), text, masked);
+        }
+        eprintln!("        ┆");
+    }
+}
+
+/// Ask on the terminal whether to write the case
+fn confirm() -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        bail!("Review the redacted lines above and re-run with --yes to write the case");
+    }
+    eprint!("  Write these lines to the case? [y/N] ");
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .context("failed to read the answer")?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Replay a case and print the outcome as text or JSON
+pub fn replay(path: &Path, trace: bool, format: Option<OutputFormat>) -> Result<Replay> {
+    let json = match format {
+        None | Some(OutputFormat::Terminal) => false,
+        Some(OutputFormat::Json) => true,
+        Some(other) => {
+            return Err(exit::usage(format!(
+                "`revet debug replay` supports --format terminal or json, not {:?}",
+                other
+            )))
+        }
+    };
+    let case = Case::read(path)?;
+    let replay = case.replay(trace)?;
+
+    if json {
+        let report = serde_json::json!({
+            "case": path,
+            "analyzer": case.manifest.analyzer,
+            "finding": case.manifest.finding,
+            "reproduced": replay.reproduced,
+            "findings": replay.findings,
+            "trace": replay.trace,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(replay);
+    }
+
+    let manifest = &case.manifest;
+    let wanted = &manifest.finding;
+    println!(
+        "  {} {} · {} ({} analyzer, captured with revet {})",
+        "case".bold(),
+        path.display(),
+        manifest.analyzer.prefix,
+        manifest.analyzer.name,
+        manifest.revet_version
+    );
+    if manifest.revet_version != revet_core::VERSION {
+        println!(
+            "  {}",
+            format!(
+                "replaying with revet {}; analyzer behaviour may have changed since",
+                revet_core::VERSION
+            )
+            .dimmed()
+        );
+    }
+    println!(
+        "  {} {} {}:{} — {}",
+        "finding".bold(),
+        wanted.id,
+        manifest.file,
+        wanted.line,
+        wanted.message
+    );
+    println!();
+
+    if trace {
+        if replay.trace.is_empty() {
+            println!("  {}", "(no decisions traced)".dimmed());
+        }
+        for event in &replay.trace {
+            let location = format!("{}:{}", event.file.display(), event.line);
+            println!(
+                "  {:<28} {:<9} {}",
+                location.dimmed(),
+                event.decision.to_string(),
+                event.detail
+            );
+        }
+        println!();
+    }
+
+    for finding in &replay.findings {
+        println!(
+            "  {} {}:{} {}",
+            finding.id.dimmed(),
+            finding.file.display(),
+            finding.line,
+            finding.redacted_message()
+        );
+    }
+    if replay.reproduced {
+        println!(
+            "  {} {} reproduces at line {}",
+            "✓".green(),
+            wanted.id,
+            wanted.line
+        );
+    } else {
+        println!(
+            "  {} {} does not reproduce ({} finding(s) on the file)",
+            "✗".red(),
+            wanted.id,
+            replay.findings.len()
+        );
+    }
+    Ok(replay)
+}
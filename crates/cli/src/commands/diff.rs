@@ -1,50 +1,461 @@
 //! Diff command — show findings only on changed lines
+//!
+//! The base is a branch or commit, or with `--merge-base` the commit HEAD
+//! branched off it at. With `--diff-base-detect gerrit` or
+//! `--parent-change` it is the parent of the change under review (see
+//! [`ci::gerrit_base`]): for a change in a stack, the change below it rather
+//! than the target branch. A change that is not checked out is read from its
+//! commit, written to a scratch directory in the state directory.
+//!
+//! `--stack` reviews every commit between the base and HEAD on its own,
+//! against its first parent, and reports the findings per change, so the
+//! reviewers of a change in the middle of a stack see only its findings.
+//! A change's baseline (`revet baseline --change`) is found by its
+//! `Change-Id`, so it still applies to a new patchset.
 
 use anyhow::Result;
 use colored::Colorize;
+use revet_core::analyzer::config_drift::ConfigSide;
+use revet_core::diff::stack::{self, StackChange};
+use revet_core::suggestion;
 use revet_core::{
-    apply_fixes, filter_findings, filter_findings_by_diff, filter_findings_by_inline,
-    AnalyzerDispatcher, Baseline, DiffAnalyzer, Finding, GateConfig, ParserDispatcher, RevetConfig,
-    Severity, SuppressedFinding,
+    apply_fixes, filter_findings, filter_findings_by_diff, filter_inline_with, AnalysisMemo,
+    AnalyzerDispatcher, Baseline, CodeGraph, CodeKinds, Constructs, CoverageLedger, CoverageReport,
+    DiffAnalyzer, DiffFileLines, DiffLineMap, Finding, FixReport, GateConfig, GeneratedFiles,
+    GitObjects, ParserDispatcher, ProfileSettings, RevetConfig, ReviewEstimate, ReviewSummary,
+    Rollout, Severity, SkipReason, StoragePaths, SuppressedFinding,
 };
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-use super::review::{build_summary, has_extension, has_filename, ReviewExitCode};
-use crate::output::{make_formatter, resolve_format};
+use super::ci::{self, GerritContext};
+use super::multi::merge_summary;
+use super::review::{build_summary, is_analysis_input, print_dropped_entries};
+use crate::exit::{self, Classify, Outcome};
+use crate::output::{
+    resolve_locale, resolve_targets, Pipeline, Reporter, RunEnd, RunStart, Target,
+};
+use crate::{BaseDetect, CoverageDetail, DiffArgs};
+
+/// Scratch directory (in the state directory) changes are checked out to
+const CHECKOUT_DIR: &str = "checkout";
+
+/// What one diff compares
+struct DiffTarget {
+    base: String,
+    /// The change under review, with `--diff-base-detect`,
+    /// `--parent-change` or `--stack`
+    change: Option<StackChange>,
+    /// Read the files of this commit instead of the working tree, which
+    /// has another one checked out
+    commit: Option<String>,
+    /// Estimate the review effort of the diff; stacked changes and baseline
+    /// runs have none
+    estimate: bool,
+}
+
+/// What every diff of a run shares
+struct DiffContext<'a> {
+    repo_path: PathBuf,
+    config: RevetConfig,
+    settings: ProfileSettings,
+    targets: Vec<Target>,
+    dispatcher: ParserDispatcher,
+    analyzer_dispatcher: AnalyzerDispatcher,
+    git_objects: GitObjects,
+    paths: StoragePaths,
+    cli: &'a crate::Cli,
+}
+
+/// The findings of one diff, before output
+struct DiffReview {
+    findings: Vec<Finding>,
+    suppressed: Vec<SuppressedFinding>,
+    /// `None` when no changed file is analyzed
+    summary: Option<ReviewSummary>,
+    coverage: Option<CoverageReport>,
+    /// Findings on unchanged lines
+    diff_filtered: usize,
+    /// Review effort and suggested reviewers, for a diff of the working tree
+    estimate: Option<ReviewEstimate>,
+}
 
-pub fn run(base: &str, cli: &crate::Cli) -> Result<ReviewExitCode> {
+pub fn run(args: &DiffArgs, cli: &crate::Cli) -> Result<Outcome> {
     let start = Instant::now();
     let repo_path = std::fs::canonicalize(Path::new(".")).unwrap_or_else(|_| PathBuf::from("."));
 
+    if args.stack {
+        return run_stack(&repo_path, args, cli, start);
+    }
+    let target = resolve_target(&repo_path, args)?;
+    if cli.fix && target.commit.is_some() {
+        return Err(exit::usage(
+            "--fix edits the working tree; check out the change to fix it",
+        ));
+    }
+
     eprintln!(
         "{}",
         format!(
             "  revet v{} — diff analysis vs {}",
             revet_core::VERSION,
+            target.base
+        )
+        .bold()
+    );
+    eprintln!();
+
+    let ctx = DiffContext::new(&repo_path, &target.base, cli)?;
+    let review = analyze(&ctx, &target)?;
+
+    let Some(summary) = &review.summary else {
+        let mut out = Pipeline::new(
+            &ctx.targets,
+            &repo_path,
+            false,
+            false,
+            resolve_locale(&ctx.config),
+        );
+        out.end_run(&RunEnd {
+            coverage: review.coverage.as_ref(),
+            ..RunEnd::no_files(start.elapsed())
+        });
+        out.finish(&ctx.config.output)?;
+        return Ok(Outcome::Success);
+    };
+
+    // ── 10. Output ───────────────────────────────────────────────
+    let outcome = ctx.outcome(summary);
+    let mut out = ctx.pipeline();
+    out.begin_run(&RunStart {
+        reproducibility: Some(
+            &ctx.settings
+                .reproducibility()
+                .with_rollout(ctx.analyzer_dispatcher.rollout())
+                .with_language_targets(&ctx.repo_path, &ctx.config),
+        ),
+        review_estimate: review.estimate.as_ref(),
+        ..RunStart::default()
+    });
+    ctx.report(&mut out, &review);
+    out.end_run(&RunEnd {
+        coverage: review.coverage.as_ref(),
+        elapsed: start.elapsed(),
+        passed: outcome.is_success(),
+        ..RunEnd::new(summary, &review.suppressed)
+    });
+    out.finish(&ctx.config.output)?;
+
+    if review.diff_filtered > 0 {
+        println!(
+            "  {}",
+            format!(
+                "{} finding(s) on unchanged lines filtered out",
+                review.diff_filtered
+            )
+            .dimmed()
+        );
+    }
+
+    Ok(outcome)
+}
+
+/// `--stack`: one diff per change, one report
+fn run_stack(
+    repo_path: &Path,
+    args: &DiffArgs,
+    cli: &crate::Cli,
+    start: Instant,
+) -> Result<Outcome> {
+    if cli.fix {
+        return Err(exit::usage(
+            "--fix edits the working tree; it is not supported with --stack",
+        ));
+    }
+    let context = match args.diff_base_detect {
+        Some(BaseDetect::Gerrit) => GerritContext::from_env(),
+        None => None,
+    };
+    let base = args
+        .parent_change
+        .clone()
+        .or_else(|| args.base.clone())
+        .or_else(|| {
+            let branch = context.as_ref()?.branch.as_ref()?;
+            Some(format!("origin/{}", branch))
+        })
+        .ok_or_else(|| {
+            exit::usage(
+                "--stack needs the commit the stack is based on, e.g. `revet diff main --stack`",
+            )
+        })?;
+    let head = if context.is_some() {
+        ci::gerrit_base(repo_path, context.as_ref(), None)
+            .environment_error()?
+            .change
+            .commit
+    } else {
+        "HEAD".to_string()
+    };
+    let changes = stack::walk(repo_path, &base, &head).environment_error()?;
+
+    eprintln!(
+        "{}",
+        format!(
+            "  revet v{} — reviewing {} change(s) on {}",
+            revet_core::VERSION,
+            changes.len(),
             base
         )
         .bold()
     );
     eprintln!();
 
-    // ── 1. Config ────────────────────────────────────────────────
-    let config = RevetConfig::find_and_load(&repo_path)?;
-    let format = resolve_format(cli, &config);
+    let ctx = DiffContext::new(repo_path, &base, cli)?;
+    let checked_out = head_commit(repo_path);
+    let mut reviews = Vec::with_capacity(changes.len());
+    for (i, change) in changes.iter().enumerate() {
+        eprintln!(
+            "  {} {}/{} {}",
+            "Change".bold(),
+            i + 1,
+            changes.len(),
+            change.label()
+        );
+        let target = DiffTarget {
+            base: change.parent.clone(),
+            change: Some(change.clone()),
+            commit: (checked_out.as_deref() != Some(change.commit.as_str()))
+                .then(|| change.commit.clone()),
+            estimate: false,
+        };
+        reviews.push(analyze(&ctx, &target)?);
+        eprintln!();
+    }
+
+    let mut total = ReviewSummary::default();
+    let mut suppressed = Vec::new();
+    let mut out = ctx.pipeline();
+    out.begin_run(&RunStart {
+        reproducibility: Some(
+            &ctx.settings
+                .reproducibility()
+                .with_rollout(ctx.analyzer_dispatcher.rollout())
+                .with_language_targets(&ctx.repo_path, &ctx.config),
+        ),
+        ..RunStart::default()
+    });
+    for (change, review) in changes.iter().zip(&reviews) {
+        out.begin_change(change);
+        ctx.report(&mut out, review);
+        if let Some(summary) = &review.summary {
+            merge_summary(&mut total, summary);
+            total.shadow.extend(summary.shadow.iter().cloned());
+        }
+        suppressed.extend(review.suppressed.iter().cloned());
+    }
+    let outcome = ctx.outcome(&total);
+    out.end_run(&RunEnd {
+        elapsed: start.elapsed(),
+        passed: outcome.is_success(),
+        ..RunEnd::new(&total, &suppressed)
+    });
+    out.finish(&ctx.config.output)?;
+
+    print_change_table(&changes, &reviews);
+    Ok(outcome)
+}
+
+/// The findings `revet diff` reports for `change`, checked out in
+/// `repo_path`, before the change's own baseline applies
+pub(crate) fn change_findings(
+    repo_path: &Path,
+    change: &StackChange,
+    cli: &crate::Cli,
+) -> Result<Vec<Finding>> {
+    let ctx = DiffContext::new(repo_path, &change.parent, cli)?;
+    let target = DiffTarget {
+        base: change.parent.clone(),
+        change: None,
+        commit: None,
+        estimate: false,
+    };
+    Ok(analyze(&ctx, &target)?.findings)
+}
+
+/// The base of a single diff, and the change under review when it is
+/// detected
+fn resolve_target(repo_path: &Path, args: &DiffArgs) -> Result<DiffTarget> {
+    if args.diff_base_detect.is_none() && args.parent_change.is_none() {
+        let mut base = args.base.clone().ok_or_else(|| {
+            exit::usage("revet diff needs a base: a branch or commit, or --diff-base-detect gerrit")
+        })?;
+        if args.merge_base {
+            let merge_base = DiffAnalyzer::new(repo_path)
+                .and_then(|diff| diff.merge_base(&base, None))
+                .environment_error()?;
+            eprintln!(
+                "  {} {} ({}...HEAD)",
+                "Merge base:".bold(),
+                merge_base.get(..12).unwrap_or(&merge_base),
+                base
+            );
+            base = merge_base;
+        }
+        return Ok(DiffTarget {
+            base,
+            change: None,
+            commit: None,
+            estimate: true,
+        });
+    }
+    if args.base.is_some() {
+        return Err(exit::usage(
+            "--diff-base-detect finds the base; drop the base argument or use --stack",
+        ));
+    }
+
+    let context = match args.diff_base_detect {
+        Some(BaseDetect::Gerrit) => GerritContext::from_env(),
+        None => None,
+    };
+    let detected = ci::gerrit_base(repo_path, context.as_ref(), args.parent_change.as_deref())
+        .environment_error()?;
+    let change = &detected.change;
+    eprintln!(
+        "  {} {}{}",
+        "Change:".bold(),
+        change.label(),
+        match detected.patchset {
+            Some((number, patchset)) => format!(" (change {}, patchset {})", number, patchset),
+            None => String::new(),
+        }
+    );
+    let commit = (head_commit(repo_path).as_deref() != Some(change.commit.as_str()))
+        .then(|| change.commit.clone());
+    Ok(DiffTarget {
+        base: detected.base,
+        change: Some(detected.change),
+        commit,
+        estimate: true,
+    })
+}
+
+fn head_commit(repo_path: &Path) -> Option<String> {
+    StackChange::resolve(repo_path, "HEAD")
+        .ok()
+        .map(|c| c.commit)
+}
+
+impl<'a> DiffContext<'a> {
+    fn new(repo_path: &Path, base: &str, cli: &'a crate::Cli) -> Result<Self> {
+        // ── 1. Config ────────────────────────────────────────────────
+        let mut config = RevetConfig::find_and_load(repo_path).usage_error()?;
+        let mut settings = crate::profile::resolve(cli, &mut config).usage_error()?;
+        settings.diff_base = base.to_string();
+        let targets = resolve_targets(cli, &config)?;
+
+        let rollout = Rollout::resolve(&config, repo_path, settings.rollout_sampling);
+        let git_objects = GitObjects::open(repo_path, &config.git)
+            .with_sparse_excluded(cli.include_sparse_excluded);
+        Ok(Self {
+            repo_path: repo_path.to_path_buf(),
+            dispatcher: ParserDispatcher::new_with_config(&config),
+            analyzer_dispatcher: AnalyzerDispatcher::new_with_config(&config)
+                .with_rollout(rollout)
+                .with_git_objects(git_objects.clone())
+                .with_memo(AnalysisMemo::session()),
+            git_objects,
+            paths: StoragePaths::resolve(repo_path, &config),
+            config,
+            settings,
+            targets,
+            cli,
+        })
+    }
+
+    fn pipeline(&self) -> Pipeline {
+        Pipeline::new(
+            &self.targets,
+            &self.repo_path,
+            self.cli.show_suppressed,
+            self.cli.verbose,
+            resolve_locale(&self.config),
+        )
+    }
+
+    /// Quality gate (--gate) takes precedence over --fail-on
+    fn outcome(&self, summary: &ReviewSummary) -> Outcome {
+        let gate = self
+            .cli
+            .gate
+            .as_deref()
+            .map(GateConfig::from_flag)
+            .unwrap_or_else(|| self.config.gate.clone());
+        Outcome::from_threshold(summary, &gate, &self.settings.fail_on)
+    }
+
+    fn report(&self, out: &mut Pipeline, review: &DiffReview) {
+        for f in &review.findings {
+            out.report_finding(f, &self.repo_path);
+        }
+        if self.cli.show_suppressed {
+            for sf in &review.suppressed {
+                out.report_suppressed(sf, &self.repo_path);
+            }
+        }
+    }
+}
+
+/// Diff `target`, reading files from a checkout of its commit when it has
+/// one
+fn analyze(ctx: &DiffContext, target: &DiffTarget) -> Result<DiffReview> {
+    let Some(commit) = &target.commit else {
+        return analyze_in(ctx, target, &ctx.repo_path);
+    };
+    let root = ctx
+        .paths
+        .write_path(CHECKOUT_DIR)
+        .join(commit.get(..12).unwrap_or(commit));
+    stack::checkout(&ctx.repo_path, commit, &root).environment_error()?;
+    let review = analyze_in(ctx, target, &root);
+    let _ = std::fs::remove_dir_all(&root);
+    let mut review = review?;
+
+    // Report the files where they are in the repository
+    let rebase = |finding: &mut Finding| {
+        if let Ok(rel) = finding.file.strip_prefix(&root) {
+            finding.file = ctx.repo_path.join(rel);
+        }
+    };
+    review.findings.iter_mut().for_each(rebase);
+    review
+        .suppressed
+        .iter_mut()
+        .for_each(|sf| rebase(&mut sf.finding));
+    Ok(review)
+}
+
+/// Diff `target` with its files read from `root`
+fn analyze_in(ctx: &DiffContext, target: &DiffTarget, root: &Path) -> Result<DiffReview> {
+    let (config, settings, cli) = (&ctx.config, &ctx.settings, ctx.cli);
+    let (dispatcher, analyzer_dispatcher) = (&ctx.dispatcher, &ctx.analyzer_dispatcher);
+    let base = target.base.as_str();
+    let head = target.commit.as_deref();
 
     // ── 2. Diff discovery ────────────────────────────────────────
-    let diff_analyzer = DiffAnalyzer::new(&repo_path)?;
+    let diff_analyzer = DiffAnalyzer::new(&ctx.repo_path)
+        .environment_error()?
+        .with_objects(ctx.git_objects.clone());
 
     eprint!("  Discovering changed files (diff vs {})... ", base);
-    let diff = diff_analyzer.get_diff(base, None)?;
+    let diff = diff_analyzer.get_diff(base, head).environment_error()?;
     let changed = diff_analyzer.get_changed_files(&diff)?;
 
-    let dispatcher = ParserDispatcher::new();
-    let analyzer_dispatcher = AnalyzerDispatcher::new_with_config(&config);
     let extensions = dispatcher.supported_extensions();
-
-    let extra_exts = analyzer_dispatcher.extra_extensions(&config);
-    let extra_names = analyzer_dispatcher.extra_filenames(&config);
+    let extra_exts = analyzer_dispatcher.extra_extensions(config);
+    let extra_names = analyzer_dispatcher.extra_filenames(config);
+    let extra_dotfiles = analyzer_dispatcher.extra_dotfiles(config);
     let mut all_extensions: Vec<&str> = extensions.clone();
     for ext in &extra_exts {
         if !all_extensions.contains(ext) {
@@ -52,35 +463,66 @@ pub fn run(base: &str, cli: &crate::Cli) -> Result<ReviewExitCode> {
         }
     }
 
-    let files: Vec<PathBuf> = changed
-        .into_iter()
-        .filter_map(|cf| {
-            if cf.change_type == revet_core::diff::ChangeType::Deleted {
-                return None;
-            }
-            let abs = repo_path.join(&cf.path);
-            if abs.exists()
-                && (has_extension(&cf.path, &all_extensions)
-                    || has_filename(&cf.path, &extra_names))
-            {
-                Some(abs)
-            } else {
-                None
+    let mut ledger = cli.coverage_report.map(|_| CoverageLedger::new());
+    let mut files: Vec<PathBuf> = Vec::new();
+    for cf in changed {
+        if cf.change_type == revet_core::diff::ChangeType::Deleted {
+            continue;
+        }
+        let abs = root.join(&cf.path);
+        if !abs.exists() {
+            continue;
+        }
+        if is_analysis_input(&cf.path, &all_extensions, &extra_names, &extra_dotfiles) {
+            if let Some(ledger) = ledger.as_mut() {
+                ledger.discovered(&abs);
             }
-        })
-        .collect();
+            files.push(abs);
+        } else if let Some(ledger) = ledger.as_mut() {
+            ledger.dropped(&abs, SkipReason::UnsupportedExtension);
+        }
+    }
+    let files = ctx.git_objects.retain_sparse(root, files, ledger.as_mut());
     eprintln!("{} ({} files)", "done".green(), files.len());
 
+    // Changes to revet's own configuration, reported however many
+    // analyzed files the diff touches
+    let drift = config_drift(
+        cli,
+        &ctx.repo_path,
+        &ctx.paths,
+        &ctx.analyzer_dispatcher,
+        &ctx.config,
+        &target.base,
+        target.commit.as_deref(),
+    );
+
+    let coverage = |ledger: &Option<CoverageLedger>| match (ledger, cli.coverage_report) {
+        (Some(ledger), Some(detail)) => {
+            let mut report = ledger.report(root, detail == CoverageDetail::Full);
+            report.degraded = ctx.git_objects.degradations();
+            Some(report)
+        }
+        _ => None,
+    };
     if files.is_empty() {
-        let mut out = make_formatter(format, &repo_path, false);
-        out.write_no_files(start.elapsed());
-        out.finalize();
-        return Ok(ReviewExitCode::Success);
+        let summary = (!drift.is_empty()).then(|| ReviewSummary {
+            degraded: ctx.git_objects.degradations(),
+            ..build_summary(&drift, &files, 0)
+        });
+        return Ok(DiffReview {
+            findings: drift,
+            suppressed: Vec::new(),
+            summary,
+            coverage: coverage(&ledger),
+            diff_filtered: 0,
+            estimate: None,
+        });
     }
 
     // ── 3. Build diff line map ───────────────────────────────────
     eprint!("  Building diff line map... ");
-    let diff_map = diff_analyzer.get_all_changed_lines(base)?;
+    let mut diff_map = diff_analyzer.changed_lines(base, head)?;
     let changed_line_count: usize = diff_map
         .values()
         .map(|v| match v {
@@ -95,20 +537,35 @@ pub fn run(base: &str, cli: &crate::Cli) -> Result<ReviewExitCode> {
         changed_line_count
     );
 
-    // ── 4. Parse (parallel) ────────────────────────────────────
-    eprint!("  Building code graph... ");
-    let graph_start = Instant::now();
-
-    let (graph, parse_errors) = dispatcher.parse_files_parallel(&files, repo_path.clone());
+    let generated = GeneratedFiles::from_config(&config.files).classify(&files);
+    let vendored = super::review::classify_vendored(config, &ctx.repo_path, root, &files);
+    let analysis_files = vendored.analysis_files(&generated.analysis_files(&files));
 
+    // ── 4. Parse (parallel) ────────────────────────────────────
+    let (graph, parse_errors) = if settings.graph {
+        eprint!("  Building code graph... ");
+        let graph_start = Instant::now();
+        let (graph, parse_errors) = dispatcher.parse_files_parallel(&files, root.to_path_buf());
+        eprintln!(
+            "{} — {} files, {} nodes ({:.1}s)",
+            "done".green(),
+            files.len(),
+            graph.nodes().count(),
+            graph_start.elapsed().as_secs_f64()
+        );
+        (graph, parse_errors)
+    } else {
+        (CodeGraph::new(root.to_path_buf()), Vec::new())
+    };
     let node_count: usize = graph.nodes().count();
-    eprintln!(
-        "{} — {} files, {} nodes ({:.1}s)",
-        "done".green(),
-        files.len(),
-        node_count,
-        graph_start.elapsed().as_secs_f64()
-    );
+    if let Some(ledger) = ledger.as_mut() {
+        if settings.graph {
+            dispatcher.record_coverage(&files, &parse_errors, ledger);
+        }
+        analyzer_dispatcher.record_coverage(&analysis_files, config, ledger);
+        generated.record(ledger);
+        vendored.record(ledger);
+    }
 
     // ── 5. Domain Analyzers ──────────────────────────────────────
     let mut findings: Vec<Finding> = Vec::new();
@@ -130,93 +587,234 @@ pub fn run(base: &str, cli: &crate::Cli) -> Result<ReviewExitCode> {
 
     eprint!("  Running domain analyzers... ");
     let analyzer_start = Instant::now();
-    let analyzer_findings = analyzer_dispatcher.run_all_parallel(&files, &repo_path, &config);
+    let analyzer_findings = analyzer_dispatcher.run_all_parallel(&analysis_files, root, config);
     let analyzer_count = analyzer_findings.len();
-    findings.extend(analyzer_findings);
+    let analyzer_findings = generated.apply(analyzer_findings, root);
+    let (analyzer_findings, upstream) = vendored.apply(analyzer_findings, root);
+    if let Some(ledger) = ledger.as_mut() {
+        ledger.vendored_upstream(upstream);
+    }
+    findings.extend(CodeKinds::from_config(&config.files).apply(analyzer_findings, root));
     eprintln!(
         "{} — {} finding(s) ({:.1}s)",
         "done".green(),
         analyzer_count,
         analyzer_start.elapsed().as_secs_f64()
     );
+    findings.retain(|f| settings.keeps(f));
+    let (kept, mut shadow) = analyzer_dispatcher.split_shadow(findings);
+    findings = kept;
+    suggestion::verify(&mut findings, root);
 
     // ── 6. Apply fixes (before filtering) ────────────────────────
+    // The findings and changed lines move with the edits, so the filters
+    // below read the fixed files at the right lines
+    let mut fix_report = None;
     if cli.fix {
         eprint!("  Applying fixes... ");
-        match apply_fixes(&findings) {
-            Ok(report) => eprintln!(
-                "{} ({} applied, {} suggestion-only)",
-                "done".green(),
-                report.applied,
-                report.skipped
-            ),
+        let mut journal = cli.journal(root, &ctx.paths);
+        match apply_fixes(&findings, &mut journal) {
+            Ok(mut report) => {
+                eprintln!(
+                    "{} ({} applied, {} suggestion-only)",
+                    "done".green(),
+                    report.applied,
+                    report.skipped
+                );
+                findings = report.reconcile(findings);
+                shadow = report.reconcile(shadow);
+                remap_changed_lines(&mut diff_map, &report, root);
+                fix_report = Some(report);
+            }
             Err(e) => eprintln!("{}: {}", "failed".red(), e),
         }
+        if let Err(e) = journal.finish() {
+            eprintln!("  warn: audit log not written: {:#}", e);
+        }
     }
 
     // ── 7. Filter by diff lines ──────────────────────────────────
-    let (new_findings, diff_filtered) = filter_findings_by_diff(findings, &diff_map, &repo_path);
+    let (new_findings, diff_filtered) = filter_findings_by_diff(findings, &diff_map, root);
     findings = new_findings;
 
     // ── 8. Inline suppression ────────────────────────────────────
     let mut all_suppressed: Vec<SuppressedFinding> = Vec::new();
-    let (new_findings, inline_suppressed) = filter_findings_by_inline(findings);
-    findings = new_findings;
-    all_suppressed.extend(inline_suppressed);
+    let constructs = Constructs::from_graph(&graph);
+    let inline = filter_inline_with(findings, &constructs);
+    findings = inline.kept;
+    all_suppressed.extend(inline.suppressed);
+
+    // Malformed and unknown suppressions on changed lines; not useless
+    // ones, since a diff run sees only part of the findings
+    let (audit_findings, audit_suppressed) =
+        analyzer_dispatcher.run_suppression_audit(&analysis_files, &inline.usage, false, config);
+    let (audit_findings, _) = filter_findings_by_diff(audit_findings, &diff_map, root);
+    findings.extend(audit_findings);
+    all_suppressed.extend(audit_suppressed);
 
     // ── 9. Baseline suppression ──────────────────────────────────
     if !cli.no_baseline {
-        if let Some(baseline) = Baseline::load(&repo_path)? {
-            let (new_findings, baseline_suppressed) =
-                filter_findings(findings, &baseline, &repo_path);
+        // The repository's baseline, then the change's own, by Change-Id
+        let change_baseline = target
+            .change
+            .as_ref()
+            .map(|change| ctx.paths.change_baseline_path(change.key()));
+        let repo_baseline = ctx.paths.baseline_read_path();
+        let mut baselines = [
+            Baseline::load_from(&repo_baseline)?.map(|b| (repo_baseline, b)),
+            match change_baseline {
+                Some(path) => Baseline::load_from(&path)?.map(|b| (path, b)),
+                None => None,
+            },
+        ];
+        if let Some(report) = fix_report
+            .as_mut()
+            .filter(|_| config.baseline.auto_reconcile)
+        {
+            reconcile_baselines(&mut baselines, report, root, ctx);
+        }
+        for (_, baseline) in baselines.iter().flatten() {
+            let (new_findings, baseline_suppressed) = filter_findings(findings, baseline, root);
             findings = new_findings;
             all_suppressed.extend(baseline_suppressed);
         }
     }
 
-    // ── 10. Output ───────────────────────────────────────────────
-    let summary = build_summary(&findings, &files, node_count);
+    // Configuration drift is about the change itself: the inline comments
+    // and baseline the change may edit don't apply to it
+    findings.extend(drift);
 
-    let mut out = make_formatter(format, &repo_path, cli.show_suppressed);
-    for f in &findings {
-        out.write_finding(f, &repo_path);
-    }
-    if cli.show_suppressed {
-        for sf in &all_suppressed {
-            out.write_suppressed(sf, &repo_path);
+    // ── 10. Session ignores (`revet ignore`) ─────────────────────
+    let session_ignored = super::ignore::active(cli, &ctx.paths)
+        .map(|ignores| super::ignore::apply(&ignores, &mut findings, &mut all_suppressed, root));
+
+    let mut summary = build_summary(&findings, &files, node_count);
+    summary.session_ignored = session_ignored;
+    // Modules sampled out of their rollout count what they would have
+    // reported on changed lines
+    let (shadow, _) = filter_findings_by_diff(shadow, &diff_map, root);
+    summary.shadow =
+        analyzer_dispatcher.shadow_summary(&filter_inline_with(shadow, &constructs).kept);
+    let estimate = (target.estimate && head.is_none()).then(|| {
+        ReviewEstimate::collect(
+            &ctx.repo_path,
+            &ctx.paths,
+            config,
+            &graph,
+            &diff_map,
+            &summary,
+            None,
+            &ctx.git_objects,
+        )
+    });
+    // Last, once everything that reads git objects has run
+    summary.degraded = ctx.git_objects.degradations();
+
+    Ok(DiffReview {
+        findings,
+        suppressed: all_suppressed,
+        summary: Some(summary),
+        coverage: coverage(&ledger),
+        diff_filtered,
+        estimate,
+    })
+}
+
+/// `CONF` findings for how a diff against `base` changes revet's
+/// configuration: the base ref's config, resolved with this run's profile
+/// and flags, against `config` (or `commit`'s, when the change is read
+/// from a commit)
+pub(crate) fn config_drift(
+    cli: &crate::Cli,
+    repo_path: &Path,
+    paths: &StoragePaths,
+    analyzer_dispatcher: &AnalyzerDispatcher,
+    config: &RevetConfig,
+    base: &str,
+    commit: Option<&str>,
+) -> Vec<Finding> {
+    let baseline = paths.baseline_read_path();
+    let objects = analyzer_dispatcher.git_objects(repo_path, config);
+    let at_ref = |ref_spec: &str| -> Result<ConfigSide> {
+        let mut side = ConfigSide::at_ref(repo_path, ref_spec, &baseline, &objects)?;
+        crate::profile::resolve(cli, &mut side.config)?;
+        Ok(side)
+    };
+    let sides = at_ref(base).and_then(|base| {
+        let head = match commit {
+            Some(commit) => at_ref(commit)?,
+            None => ConfigSide::working_tree(repo_path, config.clone(), &baseline)?,
+        };
+        Ok((base, head))
+    });
+    match sides {
+        Ok((base, head)) => analyzer_dispatcher
+            .run_config_drift(&base, &head, &baseline)
+            .unwrap_or_default(),
+        Err(e) => {
+            eprintln!("  warn: configuration drift not checked: {:#}", e);
+            Vec::new()
         }
     }
-    out.write_summary(&summary, &all_suppressed, start.elapsed(), None);
-    out.finalize();
+}
 
-    if diff_filtered > 0 {
-        println!(
-            "  {}",
-            format!(
-                "{} finding(s) on unchanged lines filtered out",
-                diff_filtered
-            )
-            .dimmed()
-        );
+/// Per-change counts, on stderr so JSON/SARIF stdout stays
+/// machine-readable
+/// Move the changed lines of the files `report` edited to their lines after
+/// the fixes
+fn remap_changed_lines(diff_map: &mut DiffLineMap, report: &FixReport, root: &Path) {
+    for (path, lines) in diff_map.iter_mut() {
+        let (DiffFileLines::Lines(set), Some(map)) =
+            (lines, report.line_maps.get(&root.join(path)))
+        else {
+            continue;
+        };
+        *set = set.iter().filter_map(|&line| map.map(line)).collect();
     }
+}
 
-    // Quality gate (--gate) takes precedence over --fail-on
-    let gate = cli
-        .gate
-        .as_deref()
-        .map(GateConfig::from_flag)
-        .unwrap_or_else(|| config.gate.clone());
-
-    let exceeded = if !gate.is_empty() {
-        summary.exceeds_gate(&gate)
-    } else {
-        let fail_on = cli.fail_on.as_deref().unwrap_or(&config.general.fail_on);
-        summary.exceeds_threshold(fail_on)
-    };
+/// `[baseline] auto_reconcile`: move the anchors of `baselines` through the
+/// fixes in `report`, and save the ones that changed
+fn reconcile_baselines(
+    baselines: &mut [Option<(PathBuf, Baseline)>],
+    report: &mut FixReport,
+    root: &Path,
+    ctx: &DiffContext,
+) {
+    let mut journal = ctx.cli.journal(root, &ctx.paths);
+    for (path, baseline) in baselines.iter_mut().flatten() {
+        if baseline.reconcile(report, root) {
+            if let Err(e) = baseline.save_to(path, &mut journal) {
+                eprintln!("  warn: reconciled baseline not saved: {:#}", e);
+            }
+        }
+    }
+    if let Err(e) = journal.finish() {
+        eprintln!("  warn: audit log not written: {:#}", e);
+    }
+    print_dropped_entries(report, root);
+}
 
-    if exceeded {
-        Ok(ReviewExitCode::FindingsExceedThreshold)
-    } else {
-        Ok(ReviewExitCode::Success)
+fn print_change_table(changes: &[StackChange], reviews: &[DiffReview]) {
+    eprintln!();
+    eprintln!(
+        "  {:<9}  {:>6}  {:>8}  {:>5}  Subject",
+        "Change".bold(),
+        "Errors",
+        "Warnings",
+        "Info"
+    );
+    eprintln!("  {}", "\u{2500}".repeat(60).dimmed());
+    for (change, review) in changes.iter().zip(reviews) {
+        let summary = review.summary.clone().unwrap_or_default();
+        eprintln!(
+            "  {:<9}  {:>6}  {:>8}  {:>5}  {}",
+            change.short(),
+            summary.errors,
+            summary.warnings,
+            summary.info,
+            change.subject
+        );
     }
+    eprintln!();
 }
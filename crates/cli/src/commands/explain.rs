@@ -1,7 +1,20 @@
 //! Explain a specific finding category in detail
+//!
+//! A finding ID (`SEC-003`) is looked up in the last review's
+//! `last-run.json`, so the finding itself is explained too: its location,
+//! the code around it as the review saw it, the suggestion and whether
+//! `revet --fix` fixes it. That works offline, without `--ai`.
 
 use anyhow::Result;
 use colored::Colorize;
+use revet_core::analyzer::registry::{prefix_of, BUILTIN};
+use revet_core::StoragePaths;
+use std::path::Path;
+
+use crate::run_log::{self, LastRun, LastRunFinding};
+
+/// Findings of the same prefix listed when an ID is not in the last run
+const NEAR_MATCHES: usize = 10;
 
 pub struct CategoryExplanation {
     pub prefix: &'static str,
@@ -220,8 +233,9 @@ const EXPLANATIONS: &[CategoryExplanation] = &[
         prefix: "SUPPRESS",
         name: "Inline Suppression",
         description: "Inline comments that silence specific findings at the source location. \
-            Add a `revet-ignore` comment on the same line or the line before a finding to \
-            suppress it. Suppressed findings are excluded from output and do not count toward \
+            Add a `revet-ignore` comment at the end of a finding's line, or on a line of its own \
+            above it, to suppress it; `revet-ignore:block` above a function or class covers all \
+            of it. Suppressed findings are excluded from output and do not count toward \
             the --fail-on threshold.",
         why_it_matters: &[
             "Makes developer intent visible directly in the code (\"this is intentional\")",
@@ -230,10 +244,12 @@ const EXPLANATIONS: &[CategoryExplanation] = &[
             "Uses stable prefixes (SEC, SQL, ML) instead of renumbered finding IDs",
         ],
         how_to_fix: &[
-            "Add `revet-ignore PREFIX` as a comment on the line before or same line as the finding",
+            "Add `revet-ignore PREFIX` as a comment at the end of the finding's line or on its own line above it",
+            "Write `revet-ignore:block PREFIX` above a function or class to cover all of it",
             "Use the finding prefix (SEC, SQL, ML, INFRA, etc.) — not the full ID like SEC-001",
             "Use `revet-ignore *` to suppress all findings on that line",
             "Combine prefixes with spaces: `revet-ignore SEC SQL` to suppress both categories",
+            "Name a single check with its sub-rule: `revet-ignore: error_handling:swallowed`",
         ],
         example_bad: r#"    password = "hardcoded123"  // triggers SEC finding"#,
         example_good: r#"    // revet-ignore SEC
@@ -318,10 +334,486 @@ const EXPLANATIONS: &[CategoryExplanation] = &[
             "Supported Languages: Python, TypeScript/JavaScript, Go, Java",
         ],
     },
+    CategoryExplanation {
+        prefix: "SUPP",
+        name: "Suppression Comment",
+        description: "A `revet-ignore` comment that names an unknown rule, is malformed, has \
+            passed its `until=` date, or no longer suppresses any finding. Unknown and malformed comments silently fail to \
+            suppress what their author intended; unused ones hide whatever appears on the line later.",
+        why_it_matters: &[
+            "A typo such as `revet-ignore SCE` suppresses nothing, and nothing says so",
+            "`revet-ignore SEC, SQL` only applies `SEC` — rules are separated by spaces",
+            "Stale suppressions outlive the code they were written for and mask new findings",
+            "A temporary exception past its `until=` date no longer hides anything",
+        ],
+        how_to_fix: &[
+            "Use a finding prefix (SEC), a module (error_handling) or a module:sub-rule",
+            "Write `revet-ignore` followed by space-separated rules",
+            "Run `revet --full --fix` to remove suppressions that match no findings",
+            "Fix what an expired suppression hid, or move `until=YYYY-MM-DD` later",
+            "Silence a deliberate exception with `revet-ignore SUPP` on the line before",
+        ],
+        example_bad: r#"    key = load()  # revet-ignore SEC, SQL"#,
+        example_good: r#"    key = load()  # revet-ignore SEC SQL"#,
+        references: &["Suppression comments: https://umitkavala.github.io/revet/docs/analyzers/suppressions"],
+    },
+    CategoryExplanation {
+        prefix: "CONF",
+        name: "Configuration Drift",
+        description: "A diff that changes what revet enforces: an analyzer disabled, a severity \
+            lowered, a threshold raised, paths or findings newly excluded, the baseline grown or \
+            `fail_on` loosened. Weakening changes are warnings, strengthening ones info. The base \
+            and head configs are compared after profiles and flags are applied.",
+        why_it_matters: &[
+            "Config diffs look routine, so a PR that switches off the analyzer it would trip is easy to miss",
+            "A raised threshold or a new exclude hides findings in every later change, not just this one",
+            "A grown baseline accepts new debt without fixing it",
+        ],
+        how_to_fix: &[
+            "Revert the change if it was only made to get the build green",
+            "Otherwise, confirm it in review; the finding points at the line that made it",
+            "Require sign-off with a `[[policy]]` on `prefix == CONF and severity == warning`",
+        ],
+        example_bad: r#"    [modules]
+    security = false  # quietly in a feature PR"#,
+        example_good: r#"    [modules]
+    security = true"#,
+        references: &["Configuration drift: https://umitkavala.github.io/revet/docs/analyzers/config-drift"],
+    },
+    CategoryExplanation {
+        prefix: "HOT",
+        name: "Hotspot",
+        description: "A file that is both changed often and complex: in the top percentile of \
+            commits touching it over the configured window and of its summed function complexity. \
+            Complex code that nobody touches costs little; complex code that changes every week \
+            is where defects and slow reviews concentrate.",
+        why_it_matters: &[
+            "Most defects land in the small set of files that are complex and frequently changed",
+            "Every change to a hotspot has to be made, reviewed and tested against its complexity",
+            "Ranking by churn × complexity turns many complexity findings into a short refactoring list",
+        ],
+        how_to_fix: &[
+            "Run `revet stats` to see the ranked hotspot table from the latest review",
+            "Split the file along the responsibilities that change independently",
+            "Simplify its most complex functions first (see the CMPLX findings for the file)",
+            "Tune `[analyzers.hotspots] window` and `percentile` to the team's release cadence",
+        ],
+        example_bad: r#"    src/billing.ts  — changed 34 times in 90 days, aggregate complexity 412"#,
+        example_good: r#"    src/billing/{invoice,tax,refund}.ts  — changes spread over small, simple files"#,
+        references: &[
+            "Your Code as a Crime Scene (Adam Tornhill)",
+            "Hotspots: https://umitkavala.github.io/revet/docs/analyzers/hotspots",
+        ],
+    },
+    CategoryExplanation {
+        prefix: "OWN",
+        name: "Orphaned code",
+        description: "A file (or directory) whose lines were last changed almost entirely by \
+            people who no longer commit: no active author owns a meaningful share of it according \
+            to git blame. Active authors come from a roster or from the commits of a recent window; \
+            paths with owners in CODEOWNERS are never reported.",
+        why_it_matters: &[
+            "Nobody left on the team knows why the code is the way it is",
+            "Changes to it are slow to review and risky to make, and tend to be avoided",
+            "Complex orphaned code on a critical path is a single point of failure for the team",
+        ],
+        how_to_fix: &[
+            "Run `revet stats` to see the orphaned files from the latest review, most lines first",
+            "Assign an owning team in CODEOWNERS",
+            "Have an active maintainer read, document and test the code before it has to change",
+            "Keep the roster in `[analyzers.ownership] active` or `roster_file` up to date",
+        ],
+        example_bad: r#"    src/legacy/export.py  — 94% of lines by departed contributors, last change 14 months ago"#,
+        example_good: r#"    /src/legacy/  @org/data-platform   # CODEOWNERS"#,
+        references: &[
+            "About code owners: https://docs.github.com/en/repositories/managing-your-repositorys-settings-and-features/customizing-your-repository/about-code-owners",
+            "Ownership: https://umitkavala.github.io/revet/docs/analyzers/ownership",
+        ],
+    },
+    CategoryExplanation {
+        prefix: "EXT",
+        name: "Imported finding",
+        description: "A finding another tool reported (Semgrep, gitleaks or any SARIF producer), \
+            imported with `revet import --from <format> <file>`. Its rule is namespaced as \
+            `EXT-<tool>-<ruleId>`; the original tool's documentation explains the rule itself.",
+        why_it_matters: &[
+            "One report, one baseline and one fail gate cover revet's and the other tools' findings",
+            "Duplicates of revet's own findings on the same line are dropped, so issues count once",
+        ],
+        how_to_fix: &[
+            "Fix the issue the original tool describes",
+            "Suppress a false positive with `# revet-ignore EXT-<tool>-<ruleId>` or `# revet-ignore EXT`",
+            "Record existing ones with `revet baseline --from <format> <file>`",
+        ],
+        example_bad: r#"    revet import --from sarif semgrep.sarif   # EXT-semgrep-python.lang.security.audit.eval"#,
+        example_good: r#"    eval(user_input)  # revet-ignore EXT-semgrep-python.lang.security.audit.eval"#,
+        references: &[
+            "Importing findings: https://umitkavala.github.io/revet/docs/commands/import",
+        ],
+    },
+    CategoryExplanation {
+        prefix: "REF",
+        name: "Refactor verification",
+        description: "A call site the diff left behind: a function whose required parameters \
+            changed, or which was renamed, is still called the old way from a line the diff did \
+            not touch. Only the changed functions are checked, against callers anywhere in the \
+            repository.",
+        why_it_matters: &[
+            "A caller passing the old number of arguments fails at runtime (Python) or at build time in another package (TypeScript)",
+            "A renamed function leaves imports and calls that only fail when that path runs",
+            "The missed caller is outside the diff, so reviewers never see it",
+        ],
+        how_to_fix: &[
+            "Update the reported call to the new signature or name",
+            "Give new parameters defaults so existing callers keep working",
+            "Keep the old name as an alias while callers migrate",
+        ],
+        example_bad: r#"    # billing.py: def charge(order, currency) — currency added in this diff
+    # refunds.py (untouched): charge(order)"#,
+        example_good: r#"    # billing.py: def charge(order, currency="EUR")
+    # refunds.py: charge(order)"#,
+        references: &[
+            "Refactor verification: https://umitkavala.github.io/revet/docs/analyzers/refactor",
+        ],
+    },
+    CategoryExplanation {
+        prefix: "I18N",
+        name: "Translations",
+        description: "Translation lookups checked against the locale catalogs: keys looked up \
+            in code that a locale does not define, catalog keys nothing looks up, namespaces a \
+            locale is missing, and keys built at runtime that cannot be checked at all.",
+        why_it_matters: &[
+            "A missing key shows users the raw key (`checkout.pay`) or falls back to another language",
+            "Translators keep paying for strings that unused keys no longer show",
+            "Keys built at runtime hide missing translations until a user hits them",
+        ],
+        how_to_fix: &[
+            "Add the key to every locale's catalog, or remove the lookup",
+            "Delete unused keys from all locales together",
+            "Look keys up by literal, e.g. through a map from values to keys",
+            "Point `[analyzers.i18n] catalogs` at the project's catalog layout",
+        ],
+        example_bad: r#"    <button>{t(`checkout.${action}`)}</button>"#,
+        example_good: r#"    <button>{t(action === "pay" ? "checkout.pay" : "checkout.cancel")}</button>"#,
+        references: &["Translations: https://umitkavala.github.io/revet/docs/analyzers/i18n"],
+    },
+    CategoryExplanation {
+        prefix: "FLAG",
+        name: "Feature flags",
+        description: "Feature-flag checks compared with the flag inventory: checks of flags that \
+            were launched or retired long ago, the branches those checks keep alive, flags the \
+            inventory does not know, entries no code checks, and flag names built at runtime.",
+        why_it_matters: &[
+            "A check of a launched flag keeps a fallback path that never runs but still has to be read, tested and maintained",
+            "Flipping a retired flag back on in the flag system silently revives code nobody remembers",
+            "Unknown flags have no owner and no plan for removal",
+        ],
+        how_to_fix: &[
+            "Delete the check of a launched flag and the branch it skips; keep the code it turns on",
+            "Delete the check of a retired flag and the code that only ran with it on",
+            "Add every flag to `.revet/flags.toml` with its state and owner, or export it from the flag system",
+            "Pass flag names as string literals so they can be checked",
+        ],
+        example_bad: r#"    if ld.variation("checkout-v2", user, False):   # launched 2024-03-01
+        return new_checkout(cart)
+    else:
+        return legacy_checkout(cart)"#,
+        example_good: r#"    return new_checkout(cart)"#,
+        references: &["Feature flags: https://umitkavala.github.io/revet/docs/analyzers/feature-flags"],
+    },
+    CategoryExplanation {
+        prefix: "DBG",
+        name: "Debug artifacts",
+        description: "Debugging leftovers in committed code: debugger statements such as \
+            `debugger;`, `breakpoint()` or `binding.pry`, prints that only say \"here\" or dump a \
+            single variable, and blocks of comments that parse as code of the file's language.",
+        why_it_matters: &[
+            "A debugger statement that reaches production hangs the request or worker that hits it",
+            "`dd()` and `dump()` halt or leak internals into responses",
+            "Stray prints clutter logs and can expose the values they dump",
+            "Commented-out code goes stale, misleads readers, and is already kept by version control",
+        ],
+        how_to_fix: &[
+            "Delete debugger statements (`revet --fix` removes the ones alone on their line)",
+            "Delete debug prints, or log through the project's logger with a real message",
+            "Delete commented-out blocks; recover them from history if they are needed again",
+        ],
+        example_bad: r#"    def total(order):
+        breakpoint()
+        print("here")
+        # for line in order.lines:
+        #     if line.discount:
+        #         line.price = apply(line.discount)
+        #     subtotal += line.price
+        # return round(subtotal, 2)
+        return sum(l.price for l in order.lines)"#,
+        example_good: r#"    def total(order):
+        return sum(l.price for l in order.lines)"#,
+        references: &["Debug artifacts: https://umitkavala.github.io/revet/docs/analyzers/debug-artifacts"],
+    },
+    CategoryExplanation {
+        prefix: "TEST",
+        name: "Test quality",
+        description: "Tests that are flaky or prove nothing: fixed sleeps such as `time.sleep(1)` or \
+            `cy.wait(1000)`, tests whose body asserts nothing, tests skipped for longer than \
+            `skip_max_age_days` according to `git blame`, and assertions that any exception \
+            satisfies. Only files classified as test code are scanned.",
+        why_it_matters: &[
+            "Fixed waits make a suite slow and fail whenever the machine is slower than the guess",
+            "A test without assertions passes as long as nothing throws, whatever the code returns",
+            "Long-skipped tests no longer describe the code, yet still look like coverage",
+            "`pytest.raises(Exception)` also passes on a typo or an unrelated failure",
+        ],
+        how_to_fix: &[
+            "Wait for the condition itself: a polling helper, an awaited event or a network alias",
+            "Assert on the result, or list your own helpers in `assertion_patterns`",
+            "Fix or delete skipped tests instead of letting the skip marker age",
+            "Expect the specific exception type, and match its message where it matters",
+        ],
+        example_bad: r#"    def test_sync(client):
+        client.start_sync()
+        time.sleep(2)
+        with pytest.raises(Exception):
+            client.fetch("missing")"#,
+        example_good: r#"    def test_sync(client):
+        client.start_sync()
+        wait_until(lambda: client.synced, timeout=5)
+        with pytest.raises(NotFound):
+            client.fetch("missing")"#,
+        references: &["Test quality: https://umitkavala.github.io/revet/docs/analyzers/test-quality"],
+    },
+    CategoryExplanation {
+        prefix: "COPY",
+        name: "Stale copy",
+        description: "User-facing text that dates the product: copyright notices ending in a past \
+            year, mentions of versions more than `version_lag` major versions behind the nearest \
+            manifest, and names listed under `[[analyzers.copy.banned]]`. Only string literals, \
+            Markdown and HTML are read; changelogs and release notes are skipped.",
+        why_it_matters: &[
+            "An old copyright year makes a site or app look abandoned",
+            "\"New in version 2\" on a version 5 page sends readers looking for features long since changed",
+            "A retired product name in the UI confuses users and undoes a rename",
+        ],
+        how_to_fix: &[
+            "Update the year (`revet --fix` does it), or render it from the current date",
+            "Drop version notes once most users are past them, or update the number",
+            "Replace retired names; `revet --fix` applies a banned term's `replacement`",
+        ],
+        example_bad: r#"    <footer>
+      © 2019 OldBrand, Inc. — new in version 2.0: dark mode
+    </footer>"#,
+        example_good: r#"    <footer>
+      © {new Date().getFullYear()} NewBrand, Inc.
+    </footer>"#,
+        references: &["Stale copy: https://umitkavala.github.io/revet/docs/analyzers/copy"],
+    },
+    CategoryExplanation {
+        prefix: "NUM",
+        name: "Numeric precision",
+        description: "Binary floating-point numbers where exact values matter: money-named \
+            variables, parameters and fields declared as floats, `==`/`!=` against a float, and \
+            JavaScript IDs given integer literals or `JSON.parse` values longer than a number \
+            keeps. Names are matched against `money_names` and `id_names` in \
+            `[analyzers.numeric]`; test code is skipped.",
+        why_it_matters: &[
+            "0.1 + 0.2 is not 0.3 in binary floating point: totals drift by cents and invoices stop adding up",
+            "An exact comparison with a computed float fails on values that print identically",
+            "JavaScript numbers keep about 15 significant digits, so 64-bit IDs round to a different record",
+        ],
+        how_to_fix: &[
+            "Hold money in a decimal type (`Decimal`, `BigDecimal`, `decimal`) or integer minor units",
+            "Compare floats within a tolerance, or compare the integers or decimals instead",
+            "Keep large IDs as strings end to end, or use BigInt",
+        ],
+        example_bad: r#"    price: float = 19.99
+    total = price * quantity
+    if total == 59.97:
+        apply_discount()"#,
+        example_good: r#"    price = Decimal("19.99")
+    total = price * quantity
+    if total == Decimal("59.97"):
+        apply_discount()"#,
+        references: &["Numeric precision: https://umitkavala.github.io/revet/docs/analyzers/numeric"],
+    },
+    CategoryExplanation {
+        prefix: "PAGE",
+        name: "Pagination",
+        description: "HTTP handlers (FastAPI, Flask, Django REST framework, Express, Fastify) that \
+            run a query reading a whole collection — `.all()`, `findMany` without `take`, a \
+            `SELECT` without `LIMIT` — and return the result without a limit, offset, cursor or \
+            page parameter. Handlers marked `revet: unbounded-ok` and tables listed in \
+            `small_tables` of `[analyzers.pagination]` pass.",
+        why_it_matters: &[
+            "An endpoint returning a whole table grows with the data: fine at launch, a timeout a year later",
+            "Every request loads and serializes every row, holding memory and database connections",
+            "Clients cannot add paging later without a breaking change to the response",
+        ],
+        how_to_fix: &[
+            "Take `limit` and `offset` (or a cursor) from the request and apply them to the query",
+            "Use the framework's pagination: DRF pagination classes, Prisma `take`/`skip`, a Fastify pagination plugin",
+            "Mark endpoints meant to return everything with `revet: unbounded-ok`, or list small tables in `small_tables`",
+        ],
+        example_bad: r#"    @app.get("/users")
+    def list_users(db: Session = Depends(get_db)):
+        return db.query(User).all()"#,
+        example_good: r#"    @app.get("/users")
+    def list_users(limit: int = 50, offset: int = 0, db: Session = Depends(get_db)):
+        return db.query(User).offset(offset).limit(limit).all()"#,
+        references: &["Pagination: https://umitkavala.github.io/revet/docs/analyzers/pagination"],
+    },
+    CategoryExplanation {
+        prefix: "A11Y",
+        name: "Accessibility",
+        description: "Accessibility mistakes that can be decided from the markup alone, in JSX and \
+            HTML templates: images without `alt`, click handlers on elements keyboards cannot \
+            reach, form fields without a label, `<html>` without `lang`, and positive `tabindex` \
+            values.",
+        why_it_matters: &[
+            "Screen readers announce an image without alt text by its file name, or not at all",
+            "A `<div onClick>` cannot be focused or activated without a mouse",
+            "An unlabelled input is read out as just \"edit text\", with no hint of what it wants",
+            "Without `lang`, screen readers pronounce the page with the wrong voice",
+            "A positive `tabindex` makes focus jump around the page out of reading order",
+        ],
+        how_to_fix: &[
+            "Describe informative images in `alt`; give decorative ones `alt=\"\"`",
+            "Use a `<button>` (or a link) for things that act on click",
+            "Wrap fields in a `<label>`, point `for`/`htmlFor` at their id, or set `aria-label`",
+            "Set `lang` on the `<html>` element",
+            "Use `tabindex=\"0\"` and order the markup the way focus should move",
+        ],
+        example_bad: r#"    <img src={avatar} />
+    <div className="close" onClick={onClose}>×</div>
+    <input type="email" placeholder="Email" />"#,
+        example_good: r#"    <img src={avatar} alt={`${user.name}'s avatar`} />
+    <button className="close" onClick={onClose}>×</button>
+    <label>Email <input type="email" /></label>"#,
+        references: &[
+            "Accessibility: https://umitkavala.github.io/revet/docs/analyzers/a11y",
+            "WCAG 2.2 quick reference: https://www.w3.org/WAI/WCAG22/quickref/",
+        ],
+    },
+    CategoryExplanation {
+        prefix: "WEBSEC",
+        name: "Web security configuration",
+        description: "CORS, cookie and security-header settings in web framework code: CORS \
+            allowing any origin together with credentials, cookies set without `HttpOnly`, \
+            `Secure` or `SameSite`, and header middleware (helmet, Flask-Talisman, Django \
+            `SECURE_*`, Spring Security `headers()`, Rails `force_ssl`) turned off or never \
+            applied. Express, Fastify, Flask, Django, Spring and Rails are recognized; values \
+            computed at runtime are reported at low confidence for verification.",
+        why_it_matters: &[
+            "With credentials, a wildcard or reflected origin lets any website act as the logged-in user and read the answers",
+            "A cookie without HttpOnly can be stolen by any injected script; without Secure it travels over plain HTTP",
+            "Without SameSite, cross-site requests carry the session cookie, which is what CSRF relies on",
+            "Turning off HSTS, framing or MIME-sniffing protection removes defenses the browser would apply for free",
+        ],
+        how_to_fix: &[
+            "List the trusted origins explicitly when credentials are allowed",
+            "Set `HttpOnly`, `Secure` and `SameSite=Lax` (or `Strict`) on session and authentication cookies",
+            "Keep the header middleware on and turn off only the single header that gets in the way, with a comment",
+        ],
+        example_bad: r#"    app.use(cors({ origin: "*", credentials: true }));
+    res.cookie("sid", token);"#,
+        example_good: r#"    app.use(cors({ origin: ["https://app.example.com"], credentials: true }));
+    res.cookie("sid", token, { httpOnly: true, secure: true, sameSite: "lax" });"#,
+        references: &[
+            "Web security configuration: https://umitkavala.github.io/revet/docs/analyzers/websec",
+            "OWASP HTTP headers cheat sheet: https://cheatsheetseries.owasp.org/cheatsheets/HTTP_Headers_Cheat_Sheet.html",
+        ],
+    },
+    CategoryExplanation {
+        prefix: "CONTRACT",
+        name: "API contract",
+        description: "Frontend API calls checked against the backend endpoints of the same \
+            repository: `fetch` and `axios` calls (and, with `[[analyzers.generated]]`, calls of a \
+            client generated from an OpenAPI spec) that no FastAPI, Flask or Express route serves, \
+            routes no call in the repository reaches, and request bodies whose fields differ from \
+            the Pydantic model the handler takes. Paths are matched as templates, so \
+            `/users/{id}`, `/users/:id` and `` `/users/${id}` `` are the same route.",
+        why_it_matters: &[
+            "A renamed route or a typo in a URL only shows up as a 404 once the page is used",
+            "A field renamed on one side is silently dropped, or rejected with a 422, at runtime",
+            "Routes nothing calls any more are code to maintain and attack surface to defend",
+        ],
+        how_to_fix: &[
+            "Fix the call's path or method, or add the missing endpoint",
+            "Declare request bodies with an interface that mirrors the backend model, or generate the client from the API spec",
+            "Set `base_paths` when a proxy serves the backend under a prefix such as `/api`",
+        ],
+        example_bad: r#"    // backend: @router.get("/users/{user_id}")
+    fetch(`/api/user/${id}`);"#,
+        example_good: r#"    // backend: @router.get("/users/{user_id}")
+    fetch(`/api/users/${id}`);"#,
+        references: &[
+            "API contract: https://umitkavala.github.io/revet/docs/analyzers/contract",
+        ],
+    },
+    CategoryExplanation {
+        prefix: "REPO",
+        name: "Repository",
+        description: "Properties of the repository as a whole rather than of a line: no LICENSE \
+            file at the root, a large baseline left untouched for months, the security analyzers \
+            turned off, and branch protection expectations declared in `[analyzers.repository]` \
+            — a CODEOWNERS file for the protected branch, required checks that are jobs of a \
+            workflow. These findings have no file or line.",
+        why_it_matters: &[
+            "Without a license the code cannot be reused, whatever the repository's visibility",
+            "A baseline that only grows hides more findings every month instead of paying them down",
+            "With `security` off, leaked secrets and injections go unreported",
+            "A required check no workflow runs blocks every merge, or is quietly dropped from the rules",
+        ],
+        how_to_fix: &[
+            "Add a LICENSE file at the repository root",
+            "Fix or expire baselined findings and re-create the baseline with `revet baseline`",
+            "Turn `security` back on in `[modules]` and suppress individual findings instead",
+            "Add a CODEOWNERS file and keep `required_checks` in step with the workflow jobs",
+        ],
+        example_bad: r#"    [modules]
+    security = false
+
+    [analyzers.repository]
+    protected_branch = "main"
+    required_checks  = ["test"]   # no workflow has a `test` job"#,
+        example_good: r#"    [modules]
+    security = true
+
+    [analyzers.repository]
+    protected_branch = "main"      # with .github/CODEOWNERS
+    required_checks  = ["test"]    # jobs: test: in .github/workflows/ci.yml"#,
+        references: &["Repository: https://umitkavala.github.io/revet/docs/analyzers/repository"],
+    },
+    CategoryExplanation {
+        prefix: "UPGRADE",
+        name: "Upgrade advisor",
+        description: "Uses of APIs that the runtime version being upgraded to removed, changed \
+            or deprecated, from bundled Python and Node.js knowledge packs. The target comes \
+            from `[upgrade]` or from `requires-python` / `engines.node`; uses are found \
+            through the imports and calls in the code graph.",
+        why_it_matters: &[
+            "Code importing a removed module fails at import time, often only in production",
+            "Changed behavior passes the type checker and shows up as a runtime error",
+            "Deprecations with a scheduled removal become tomorrow's blockers",
+        ],
+        how_to_fix: &[
+            "Move to the replacement the finding names before bumping the version",
+            "Run `revet upgrade-report --target python=3.13` to see what blocks an upgrade",
+            "Add a pack under `[upgrade] packs` for APIs the bundled packs do not cover",
+        ],
+        example_bad: r#"    from distutils.version import LooseVersion   # removed in Python 3.12
+    loop = asyncio.get_event_loop()"#,
+        example_good: r#"    from packaging.version import Version
+    asyncio.run(main())"#,
+        references: &[
+            "Upgrade advisor: https://umitkavala.github.io/revet/docs/analyzers/upgrade",
+            "What's new in Python: https://docs.python.org/3/whatsnew/",
+            "Node.js deprecations: https://nodejs.org/api/deprecations.html",
+        ],
+    },
 ];
 
 pub fn extract_prefix(finding_id: &str) -> &str {
-    finding_id.split('-').next().unwrap_or(finding_id)
+    prefix_of(finding_id)
 }
 
 pub fn get_explanation(prefix: &str) -> Option<&'static CategoryExplanation> {
@@ -382,6 +874,125 @@ fn print_explanation(explanation: &CategoryExplanation) {
     println!();
 }
 
+/// The last review of the repository in the current directory, when
+/// `finding_id` names a finding rather than a bare prefix
+fn last_run(finding_id: &str) -> Option<LastRun> {
+    if finding_id == prefix_of(finding_id) {
+        return None;
+    }
+    run_log::load_last_run(&StoragePaths::discover(Path::new(".")))
+}
+
+/// The finding itself as the last run reported it: where, the code around
+/// it, its details and suggestion, and whether a fix is available
+fn print_finding(finding: &LastRunFinding) {
+    let location = if finding.file.is_empty() {
+        "(repository)".to_string()
+    } else {
+        format!("{}:{}", finding.file, finding.line)
+    };
+    println!("  {} {}", "This Finding:".bold(), location.cyan());
+    println!("    {} {}", finding.severity.bold(), finding.message);
+    if let Some(rule) = &finding.rule {
+        match &finding.rule_description {
+            Some(description) => println!("    {} {} — {}", "Rule:".dimmed(), rule, description),
+            None => println!("    {} {}", "Rule:".dimmed(), rule),
+        }
+    }
+    if let Some(reason) = &finding.suppression_reason {
+        println!("    {} {}", "Suppressed:".dimmed(), reason);
+    }
+    if !finding.snippet.is_empty() {
+        println!();
+        for line in &finding.snippet {
+            let marker = if line.line == finding.line { ">" } else { " " };
+            println!(
+                "    {} {:>5} │ {}",
+                marker,
+                line.line.to_string().dimmed(),
+                line.text
+            );
+        }
+    }
+    if let Some(details) = &finding.details {
+        println!();
+        for line in details.lines() {
+            println!("    {}", line.dimmed());
+        }
+    }
+    println!();
+    if let Some(suggestion) = &finding.suggestion {
+        println!("    {} {}", "Suggestion:".bold(), suggestion);
+    }
+    if finding.is_fixable() {
+        println!(
+            "    {} available — run {}",
+            "Auto-fix:".bold(),
+            "revet --fix".bold()
+        );
+    } else {
+        println!("    {} not available", "Auto-fix:".bold());
+    }
+    println!();
+}
+
+/// `finding_id` is not in the last run: the findings of its prefix that
+/// are, closest ID first
+fn print_missing(finding_id: &str, run: Option<&LastRun>) {
+    let Some(run) = run else {
+        eprintln!(
+            "  {} No findings recorded yet — run {} first to explain {}",
+            "Note:".yellow().bold(),
+            "revet".bold(),
+            finding_id
+        );
+        eprintln!();
+        return;
+    };
+    eprintln!(
+        "  {} {} is not in the last run ({})",
+        "Note:".yellow().bold(),
+        finding_id,
+        run.run_id
+    );
+    let near = near_matches(finding_id, &run.findings);
+    if !near.is_empty() {
+        eprintln!("  Findings with the same prefix:");
+        for finding in near.iter().take(NEAR_MATCHES) {
+            eprintln!(
+                "    {} {}:{} {}",
+                finding.id.cyan().bold(),
+                finding.file,
+                finding.line,
+                finding.message.dimmed()
+            );
+        }
+        if near.len() > NEAR_MATCHES {
+            eprintln!("    … and {} more", near.len() - NEAR_MATCHES);
+        }
+    }
+    eprintln!();
+}
+
+/// Findings sharing `finding_id`'s prefix, by distance of their number to
+/// its number
+fn near_matches<'a>(finding_id: &str, findings: &'a [LastRunFinding]) -> Vec<&'a LastRunFinding> {
+    let prefix = prefix_of(finding_id);
+    let number = |id: &str| {
+        id.rsplit_once('-')
+            .and_then(|(_, n)| n.parse::<i64>().ok())
+            .unwrap_or(0)
+    };
+    let wanted = number(finding_id);
+    let mut near: Vec<&LastRunFinding> = findings
+        .iter()
+        .filter(|f| prefix_of(&f.id).eq_ignore_ascii_case(prefix))
+        .collect();
+    near.sort_by_key(|f| ((number(&f.id) - wanted).abs(), f.id.clone()));
+    near.dedup_by(|a, b| a.id == b.id);
+    near
+}
+
 pub fn run(finding_id: &str, use_ai: bool) -> Result<()> {
     if use_ai {
         eprintln!(
@@ -393,12 +1004,33 @@ pub fn run(finding_id: &str, use_ai: bool) -> Result<()> {
     }
 
     let prefix = extract_prefix(finding_id);
+    let run = last_run(finding_id);
+    let finding = run.as_ref().and_then(|run| {
+        run.findings
+            .iter()
+            .filter(|f| f.id.eq_ignore_ascii_case(finding_id))
+            // A finding the run reported over one it already suppressed
+            .min_by_key(|f| f.suppression_reason.is_some())
+    });
 
-    match get_explanation(prefix) {
-        Some(explanation) => {
-            print_explanation(explanation);
+    match (get_explanation(prefix), finding) {
+        (Some(explanation), _) => print_explanation(explanation),
+        // A prefix without a write-up: the finding is explained on its own
+        (None, Some(_)) => {
+            let name = BUILTIN
+                .iter()
+                .find(|b| b.prefix == prefix)
+                .map_or(prefix, |b| b.name);
+            println!();
+            println!(
+                "  {} {} {}",
+                prefix.bold().cyan(),
+                "\u{2014}".dimmed(),
+                name.bold()
+            );
+            println!();
         }
-        None => {
+        (None, None) => {
             eprintln!(
                 "  {} Unknown finding prefix: {}",
                 "Error:".red().bold(),
@@ -422,8 +1054,15 @@ pub fn run(finding_id: &str, use_ai: bool) -> Result<()> {
             );
             eprintln!("  Example: {}", "revet explain SEC-001".bold());
             eprintln!();
+            return Ok(());
         }
     }
 
+    match finding {
+        Some(finding) => print_finding(finding),
+        None if finding_id != prefix => print_missing(finding_id, run.as_ref()),
+        None => {}
+    }
+
     Ok(())
 }
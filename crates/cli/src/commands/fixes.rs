@@ -0,0 +1,52 @@
+//! `revet fixes` — findings and quick fixes for one file, for editor code actions
+
+use anyhow::{Context, Result};
+use revet_core::{analyze_buffer, filter_findings_by_path_rules, lsp, GraphContext, RevetConfig};
+use std::io::Read;
+use std::path::Path;
+
+use crate::exit;
+use crate::{Cli, OutputFormat};
+
+pub fn run(file: &Path, stdin: bool, cli: &Cli) -> Result<()> {
+    match cli.single_format()? {
+        None | Some(OutputFormat::LspJson) => {}
+        Some(other) => {
+            return Err(exit::usage(format!(
+                "`revet fixes` supports --format lsp-json, not {:?}",
+                other
+            )))
+        }
+    }
+
+    let repo_path = std::fs::canonicalize(".")?;
+    let path = if file.is_absolute() {
+        file.to_path_buf()
+    } else {
+        repo_path.join(file)
+    };
+    let path = std::fs::canonicalize(&path).unwrap_or(path);
+
+    let content = if stdin {
+        let mut buffer = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buffer)
+            .context("Failed to read buffer from stdin")?;
+        buffer
+    } else {
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+    };
+
+    let mut config = RevetConfig::find_and_load(&repo_path)?;
+    crate::profile::resolve(cli, &mut config)?;
+
+    let ctx = GraphContext::load(&repo_path, &config)?;
+    let findings = analyze_buffer(&path, &content, &config, &ctx);
+    let (findings, _) =
+        filter_findings_by_path_rules(findings, &config.ignore.per_path, ctx.repo_root());
+
+    let fixes = lsp::fixes_for_document(&findings, &path, &content);
+    println!("{}", serde_json::to_string_pretty(&fixes)?);
+    Ok(())
+}
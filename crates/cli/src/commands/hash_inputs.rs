@@ -0,0 +1,109 @@
+//! `revet hash-inputs` — the digest of a monorepo project's inputs, for Nx
+//! and Turborepo task caching, and the `--assert-hash` check of `revet
+//! review`
+
+use anyhow::Result;
+use colored::Colorize;
+use revet_core::{
+    discover_files_with_dotfiles, AnalyzerDispatcher, HashInputs, ParserDispatcher, Project,
+    RevetConfig, StoragePaths,
+};
+use std::path::{Path, PathBuf};
+
+use crate::exit::{self, Classify, Outcome};
+use crate::{Cli, OutputFormat};
+
+pub fn run(path: Option<&Path>, cli: &Cli) -> Result<()> {
+    let json = match cli.single_format()? {
+        None | Some(OutputFormat::Terminal) => false,
+        Some(OutputFormat::Json) => true,
+        Some(other) => {
+            return Err(exit::usage(format!(
+                "`revet hash-inputs` supports --format terminal or json, not {:?}",
+                other
+            )))
+        }
+    };
+
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+    let inputs = collect(&repo_path, cli)?;
+    if json {
+        let report = serde_json::json!({ "digest": inputs.digest(), "inputs": inputs });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", inputs.digest());
+    }
+    Ok(())
+}
+
+/// The inputs of `--project` in the repository at `repo_path`, with the
+/// profile and flags resolved as `revet review --project` resolves them
+pub fn collect(repo_path: &Path, cli: &Cli) -> Result<HashInputs> {
+    let dir = cli
+        .project
+        .as_deref()
+        .ok_or_else(|| exit::usage("`revet hash-inputs` needs --project DIR"))?;
+    let mut config = RevetConfig::find_and_load(repo_path).usage_error()?;
+    let settings = crate::profile::resolve_review(cli, repo_path, &mut config).usage_error()?;
+    let project = Project::resolve(repo_path, dir, &config).usage_error()?;
+
+    let files = discover_all(repo_path, &config)?;
+    let analyzers = AnalyzerDispatcher::new_with_config(&config);
+    let mut modules = analyzers.modules(&config);
+    if settings.graph {
+        for module in analyzers.graph_modules(&config) {
+            if !modules.contains(&module) {
+                modules.push(module);
+            }
+        }
+    }
+    let baseline =
+        (!cli.no_baseline).then(|| StoragePaths::resolve(repo_path, &config).baseline_read_path());
+
+    HashInputs::collect(
+        repo_path,
+        &project,
+        &files,
+        &config,
+        &settings,
+        &modules,
+        baseline.as_deref(),
+    )
+}
+
+/// `--assert-hash`: `None` when the project's inputs still hash to the
+/// digest, or the stale outcome
+pub fn check(repo_path: &Path, cli: &Cli, expected: &str) -> Result<Option<Outcome>> {
+    let digest = collect(repo_path, cli)?.digest();
+    if digest.eq_ignore_ascii_case(expected.trim()) {
+        return Ok(None);
+    }
+    eprintln!(
+        "  {} the inputs of {} changed: they hash to {}, not {}",
+        "stale:".yellow().bold(),
+        cli.project.as_deref().unwrap_or(Path::new(".")).display(),
+        digest,
+        expected.trim()
+    );
+    Ok(Some(Outcome::StaleInputs { digest }))
+}
+
+/// Every file a full scan of the repository reviews with `config`
+fn discover_all(repo_path: &Path, config: &RevetConfig) -> Result<Vec<PathBuf>> {
+    let parsers = ParserDispatcher::new_with_config(config);
+    let analyzers = AnalyzerDispatcher::new_with_config(config);
+    let mut extensions = parsers.supported_extensions();
+    for ext in analyzers.extra_extensions(config) {
+        if !extensions.contains(&ext) {
+            extensions.push(ext);
+        }
+    }
+    discover_files_with_dotfiles(
+        repo_path,
+        &extensions,
+        &analyzers.extra_filenames(config),
+        &analyzers.extra_dotfiles(config),
+        &config.ignore.paths,
+    )
+}
@@ -0,0 +1,190 @@
+//! `revet ignore` — hide findings on this machine for a while
+//!
+//! `add` records an entry for a finding of the last run or for a rule
+//! and/or path glob, `list` shows the entries still in effect and `clear`
+//! removes them. See [`revet_core::session_ignore`] for how entries match
+//! and where they are kept; [`active`] and [`apply`] are what review, diff
+//! and watch runs use.
+
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use revet_core::session_ignore::{self, SessionIgnore, SessionIgnores};
+use revet_core::{Finding, StoragePaths, SuppressedFinding};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::exit;
+use crate::run_log;
+use crate::Cli;
+
+/// What `revet ignore add` hides
+#[derive(Debug, Clone, Copy)]
+pub enum Target<'a> {
+    /// A finding of the last run, by ID
+    Finding(&'a str),
+    /// Every finding of a rule and/or under a path glob
+    Rule {
+        rule: Option<&'a str>,
+        path: Option<&'a str>,
+    },
+}
+
+fn repo_path(path: Option<&Path>) -> PathBuf {
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf())
+}
+
+/// The entries file of `paths`, or an error when there is no home
+/// directory to keep it in
+fn ignores_path(paths: &StoragePaths) -> Result<PathBuf> {
+    SessionIgnores::path(paths).ok_or_else(|| {
+        anyhow!(
+            "No local directory for session ignores — set HOME, XDG_STATE_HOME or {}",
+            revet_core::storage::DATA_DIR_ENV
+        )
+    })
+}
+
+pub fn add(path: Option<&Path>, target: Target, ttl: Duration) -> Result<SessionIgnore> {
+    let repo_path = repo_path(path);
+    let paths = StoragePaths::discover(&repo_path);
+    let now = session_ignore::now();
+    let entry = match target {
+        Target::Finding(id) => {
+            let finding = last_run_finding(&paths, &repo_path, id)?;
+            SessionIgnore::finding(&finding.id, &finding, &repo_path, now, ttl)
+        }
+        Target::Rule { rule, path } => SessionIgnore::rule(rule, path, now, ttl)
+            .map_err(|e| exit::usage(format!("{:#}", e)))?,
+    };
+
+    let file = ignores_path(&paths)?;
+    let mut ignores = SessionIgnores::load(&file)?;
+    ignores.prune(now);
+    ignores.entries.push(entry.clone());
+    ignores.save(&file)?;
+
+    eprintln!(
+        "  {} {} for {}",
+        "ignored".green(),
+        entry.describe(),
+        format_remaining(ttl)
+    );
+    if session_ignore::in_ci() {
+        eprintln!(
+            "  {}",
+            "This looks like a CI environment: session ignores don't apply here".yellow()
+        );
+    }
+    Ok(entry)
+}
+
+pub fn list(path: Option<&Path>) -> Result<()> {
+    let paths = StoragePaths::discover(&repo_path(path));
+    let file = ignores_path(&paths)?;
+    let mut ignores = SessionIgnores::load(&file)?;
+    let now = session_ignore::now();
+    if ignores.prune(now) > 0 {
+        ignores.save(&file)?;
+    }
+    if ignores.entries.is_empty() {
+        println!("  {}", "No session ignores.".dimmed());
+        return Ok(());
+    }
+    for entry in &ignores.entries {
+        let left = entry.remaining(now).unwrap_or_default();
+        println!(
+            "  {:<12} {}",
+            format!("{} left", format_remaining(left)),
+            entry.describe()
+        );
+    }
+    Ok(())
+}
+
+pub fn clear(path: Option<&Path>) -> Result<usize> {
+    let paths = StoragePaths::discover(&repo_path(path));
+    let file = ignores_path(&paths)?;
+    let cleared = SessionIgnores::load(&file)?.entries.len();
+    SessionIgnores::default().save(&file)?;
+    eprintln!("  {} session ignore(s) cleared", cleared);
+    Ok(cleared)
+}
+
+/// The session ignores a run applies: none in CI, with
+/// `--no-session-ignores`, or when every entry has expired. Entries that
+/// don't load are reported and skipped.
+pub(crate) fn active(cli: &Cli, paths: &StoragePaths) -> Option<SessionIgnores> {
+    if cli.no_session_ignores || session_ignore::in_ci() {
+        return None;
+    }
+    let file = SessionIgnores::path(paths)?;
+    match SessionIgnores::load(&file) {
+        Ok(ignores) => ignores.is_active(session_ignore::now()).then_some(ignores),
+        Err(e) => {
+            eprintln!("  warn: session ignores not applied: {:#}", e);
+            None
+        }
+    }
+}
+
+/// Apply `ignores` to `findings`, moving those they cover to `suppressed`.
+/// Returns the number hidden, for [`ReviewSummary::session_ignored`].
+///
+/// [`ReviewSummary::session_ignored`]: revet_core::ReviewSummary::session_ignored
+pub(crate) fn apply(
+    ignores: &SessionIgnores,
+    findings: &mut Vec<Finding>,
+    suppressed: &mut Vec<SuppressedFinding>,
+    repo_root: &Path,
+) -> usize {
+    let (kept, ignored) = ignores.apply(std::mem::take(findings), repo_root, session_ignore::now());
+    *findings = kept;
+    let count = ignored.len();
+    suppressed.extend(ignored);
+    count
+}
+
+/// `id` in the newest run log, as a finding to match against later runs
+fn last_run_finding(paths: &StoragePaths, repo_path: &Path, id: &str) -> Result<Finding> {
+    let run_id = run_log::latest_run_id(paths)
+        .ok_or_else(|| exit::usage("No run logs found. Run `revet review` first."))?;
+    let log = run_log::load_run_log(paths, &run_id)?;
+    let recorded = log
+        .findings
+        .into_iter()
+        .filter(|f| f.id.eq_ignore_ascii_case(id))
+        // A finding the run reported over one it already suppressed
+        .min_by_key(|f| f.suppressed)
+        .ok_or_else(|| {
+            exit::usage(format!(
+                "Finding {} is not in the last run ({})",
+                id, run_id
+            ))
+        })?;
+    Ok(Finding {
+        id: recorded.id,
+        message: recorded.message,
+        file: if recorded.file.is_empty() {
+            PathBuf::new()
+        } else {
+            repo_path.join(&recorded.file)
+        },
+        line: recorded.line,
+        rule: recorded.rule,
+        generated: recorded.generated,
+        source_tool: recorded.source_tool,
+        ..Default::default()
+    })
+}
+
+/// `7h 59m`, `45m`, `30s`
+fn format_remaining(left: Duration) -> String {
+    let secs = left.as_secs();
+    match (secs / 3600, secs % 3600 / 60) {
+        (0, 0) => format!("{}s", secs),
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
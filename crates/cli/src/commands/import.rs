@@ -0,0 +1,77 @@
+//! `revet import` — a review with other tools' findings merged in
+//!
+//! Each `--from <format> <file>` report is converted into `EXT` findings
+//! (see [`revet_core::import`]) before the review starts; the review merges
+//! them after its analyzers, so suppression, the baseline, `[[policy]]` and
+//! the fail gate apply to them like to revet's own. Report paths are
+//! resolved against the repository after `--path-prefix-strip`.
+
+use anyhow::Result;
+use colored::Colorize;
+use revet_core::import::{self, ImportFormat, ImportOptions};
+use revet_core::Finding;
+use std::path::Path;
+
+use super::review;
+use crate::exit::{self, Classify, Outcome};
+use crate::output::resolve_targets;
+use crate::ImportArgs;
+
+pub fn run(path: Option<&Path>, reports: &ImportArgs, cli: &crate::Cli) -> Result<Outcome> {
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+    if reports.from.is_empty() {
+        return Err(exit::usage(
+            "revet import needs at least one --from <format> <file>",
+        ));
+    }
+
+    eprintln!(
+        "{}",
+        format!("  revet v{} — analyzing repository", revet_core::VERSION)
+            .bold()
+            .yellow()
+    );
+    eprintln!();
+
+    let imported = load(&repo_path, reports)?;
+    let review = review::analyze_with_imports(&repo_path, cli, imported)?;
+    let targets = resolve_targets(cli, &review.config)?;
+    review::output(&review, &targets, cli)?;
+    if cli.github_job_summary {
+        review::write_job_summary(&review, cli);
+    }
+
+    Ok(review.outcome(cli))
+}
+
+/// The findings of every `--from` report, in order. An unknown format or
+/// an unreadable report is a usage error.
+pub fn load(repo_path: &Path, reports: &ImportArgs) -> Result<Vec<Finding>> {
+    let options = ImportOptions {
+        strip_prefix: reports.path_prefix_strip.clone(),
+        ..ImportOptions::new(repo_path)
+    };
+    let mut findings = Vec::new();
+    for pair in reports.from.chunks(2) {
+        let [format, file] = pair else {
+            continue;
+        };
+        let Some(format) = ImportFormat::parse(format) else {
+            return Err(exit::usage(format!(
+                "--from {:?}: unknown report format. Expected one of: {}",
+                format,
+                ImportFormat::ALL.join(", ")
+            )));
+        };
+        let imported = import::load(format, Path::new(file), &options).usage_error()?;
+        eprintln!(
+            "  {} {} finding(s) from {}",
+            "Imported".green(),
+            imported.len(),
+            file
+        );
+        findings.extend(imported);
+    }
+    Ok(findings)
+}
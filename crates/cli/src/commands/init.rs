@@ -1,9 +1,35 @@
 //! Initialize .revet.toml configuration
 
 use anyhow::Result;
-use revet_core::RevetConfig;
+use revet_core::{discover_files_extended, RevetConfig};
+use std::collections::BTreeMap;
 use std::path::Path;
 
+use super::review::ext_to_language;
+
+/// Extensions and file names that decide which modules init suggests
+const LANGUAGE_EXTENSIONS: &[&str] = &[
+    ".rs", ".py", ".ts", ".tsx", ".js", ".jsx", ".go", ".java", ".tf", ".tfvars",
+];
+const LANGUAGE_FILENAMES: &[&str] = &["Dockerfile"];
+
+/// Modules off by default that pay off for a language in the repository
+const SUGGESTED: &[(&str, &[&str])] = &[
+    ("react", &["TypeScript", "JavaScript"]),
+    ("async_patterns", &["TypeScript", "JavaScript", "Python"]),
+    (
+        "error_handling",
+        &["Rust", "Python", "TypeScript", "JavaScript", "Go", "Java"],
+    ),
+    ("infra", &["Terraform", "Dockerfile"]),
+];
+
+/// A module init enables, and the languages that warrant it
+pub struct Suggestion {
+    pub module: &'static str,
+    pub languages: Vec<&'static str>,
+}
+
 pub fn run(path: Option<&Path>) -> Result<()> {
     let target_path = path.unwrap_or_else(|| Path::new("."));
     let config_path = target_path.join(".revet.toml");
@@ -13,12 +39,47 @@ pub fn run(path: Option<&Path>) -> Result<()> {
         return Ok(());
     }
 
-    let config = RevetConfig::default();
+    let suggestions = suggestions(target_path)?;
+    let mut config = RevetConfig::default();
+    for suggestion in &suggestions {
+        config.modules.set(suggestion.module, true);
+    }
     config.save(&config_path)?;
 
     println!("✅ Created .revet.toml at {:?}", config_path);
+    if !suggestions.is_empty() {
+        println!("\nEnabled for the languages found:");
+        for suggestion in &suggestions {
+            println!(
+                "  {:<16} {}",
+                suggestion.module,
+                suggestion.languages.join(", ")
+            );
+        }
+    }
     println!("\nYou can now customize the configuration and run:");
     println!("  revet");
 
     Ok(())
 }
+
+/// Modules worth enabling for the languages in `repo_path`, in
+/// [`SUGGESTED`] order
+pub fn suggestions(repo_path: &Path) -> Result<Vec<Suggestion>> {
+    let files = discover_files_extended(repo_path, LANGUAGE_EXTENSIONS, LANGUAGE_FILENAMES, &[])?;
+    let mut found: BTreeMap<String, usize> = BTreeMap::new();
+    for file in &files {
+        *found.entry(ext_to_language(file)).or_default() += 1;
+    }
+    Ok(SUGGESTED
+        .iter()
+        .filter_map(|(module, languages)| {
+            let languages: Vec<&str> = languages
+                .iter()
+                .copied()
+                .filter(|l| found.contains_key(*l))
+                .collect();
+            (!languages.is_empty()).then_some(Suggestion { module, languages })
+        })
+        .collect())
+}
@@ -0,0 +1,145 @@
+//! `revet issues sync` — issues for the findings that persist on the
+//! default branch, closed again once the finding is gone (see
+//! [`crate::issues`])
+//!
+//! Only full runs recorded on the branch count, read from the run logs.
+
+use anyhow::Result;
+use colored::Colorize;
+use revet_core::{RevetConfig, StoragePaths};
+use std::path::Path;
+
+use crate::exit::{Classify, Outcome};
+use crate::issues::github::GithubTracker;
+use crate::issues::jira::JiraTracker;
+use crate::issues::{self, DryRun, Selection, SyncReport, Tracker};
+use crate::run_log;
+
+/// Flags of `revet issues sync`; each overrides its `[issues]` setting
+pub struct SyncArgs<'a> {
+    pub tracker: crate::Tracker,
+    pub min_severity: Option<&'a str>,
+    pub min_age_runs: Option<usize>,
+    pub rules: &'a [String],
+    pub branch: Option<&'a str>,
+    pub dry_run: bool,
+}
+
+pub fn sync(path: Option<&Path>, args: &SyncArgs) -> Result<Outcome> {
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+    let config = RevetConfig::find_and_load(&repo_path).usage_error()?;
+
+    let mut selection = Selection::from_config(&config.issues);
+    if let Some(severity) = args.min_severity {
+        selection.min_severity = severity.to_string();
+    }
+    if let Some(runs) = args.min_age_runs {
+        selection.min_age_runs = runs;
+    }
+    if !args.rules.is_empty() {
+        selection.rules = args.rules.to_vec();
+    }
+    let branch = args
+        .branch
+        .map(str::to_string)
+        .or_else(|| config.issues.branch.clone())
+        .unwrap_or_else(|| config.general.diff_base.clone());
+
+    let paths = StoragePaths::resolve(&repo_path, &config);
+    let history = run_log::branch_history(&paths, &branch);
+    if history.is_empty() {
+        eprintln!(
+            "  {}",
+            format!(
+                "No full runs on {} recorded yet. Run `revet review --full` on it first.",
+                branch
+            )
+            .dimmed()
+        );
+        return Ok(Outcome::Success);
+    }
+
+    let tracker: Box<dyn Tracker> = match args.tracker {
+        crate::Tracker::Github => Box::new(GithubTracker::from_env(&config.issues)?),
+        crate::Tracker::Jira => Box::new(JiraTracker::from_env(&config.issues)?),
+    };
+    let dry_run = DryRun(tracker.as_ref());
+    let tracker: &dyn Tracker = if args.dry_run {
+        &dry_run
+    } else {
+        tracker.as_ref()
+    };
+
+    let report = run(tracker, &history, &selection).environment_error()?;
+    eprintln!(
+        "  {} full runs on {}; {} tracked findings",
+        history.len(),
+        branch,
+        report.tracked
+    );
+    print_report(&report.sync, tracker.name(), args.dry_run);
+
+    if report.sync.failures.is_empty() {
+        Ok(Outcome::Success)
+    } else {
+        Ok(Outcome::IssuesFailed {
+            failures: report.sync.failures.len(),
+        })
+    }
+}
+
+/// What [`run`] found and did
+pub struct RunReport {
+    /// Findings old enough to be tracked
+    pub tracked: usize,
+    pub sync: SyncReport,
+}
+
+/// Sync `tracker` with `history` (full runs, newest first). Fails only when
+/// the open issues can't be listed: without them a sync would duplicate
+/// every issue.
+pub fn run(
+    tracker: &dyn Tracker,
+    history: &[run_log::RunLog],
+    selection: &Selection,
+) -> Result<RunReport> {
+    let open = tracker.open_issues()?;
+    let persistent = issues::persistent(history, selection);
+    let latest = history.first().map(issues::reported).unwrap_or_default();
+    let plan = issues::plan(&persistent, &latest, open);
+    Ok(RunReport {
+        tracked: persistent.len(),
+        sync: issues::sync(tracker, plan),
+    })
+}
+
+fn print_report(report: &SyncReport, tracker: &str, dry_run: bool) {
+    let (create, close) = if dry_run {
+        ("Would create", "Would close")
+    } else {
+        ("Created", "Closed")
+    };
+    let line = |issue: &issues::Issue| {
+        let mut line = issue.title.clone();
+        if !issue.key.is_empty() {
+            line = format!("{} {}", issue.key, line);
+        }
+        if !issue.url.is_empty() {
+            line = format!("{} {}", line, issue.url.dimmed());
+        }
+        line
+    };
+    for issue in &report.created {
+        eprintln!("  {} {}", create.green(), line(issue));
+    }
+    for issue in &report.closed {
+        eprintln!("  {} {}", close.cyan(), line(issue));
+    }
+    for failure in &report.failures {
+        eprintln!("  {} {}", "Failed".red(), failure);
+    }
+    if report.created.is_empty() && report.closed.is_empty() && report.failures.is_empty() {
+        eprintln!("  {}", format!("{} is up to date", tracker).dimmed());
+    }
+}
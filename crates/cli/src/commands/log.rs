@@ -2,19 +2,21 @@
 
 use anyhow::Result;
 use colored::Colorize;
+use revet_core::StoragePaths;
 use std::path::Path;
 
 use crate::run_log;
 
 pub fn run(repo_path: &Path, show: Option<&str>) -> Result<()> {
+    let paths = StoragePaths::discover(repo_path);
     match show {
-        Some(id) => show_run(repo_path, id),
-        None => list_runs(repo_path),
+        Some(id) => show_run(&paths, id),
+        None => list_runs(&paths),
     }
 }
 
-fn list_runs(repo_path: &Path) -> Result<()> {
-    let entries = run_log::list_runs(repo_path)?;
+fn list_runs(paths: &StoragePaths) -> Result<()> {
+    let entries = run_log::list_runs(paths)?;
 
     if entries.is_empty() {
         println!(
@@ -55,8 +57,8 @@ fn list_runs(repo_path: &Path) -> Result<()> {
     Ok(())
 }
 
-fn show_run(repo_path: &Path, id: &str) -> Result<()> {
-    let log = run_log::load_run_log(repo_path, id)?;
+fn show_run(paths: &StoragePaths, id: &str) -> Result<()> {
+    let log = run_log::load_run_log(paths, id)?;
     let json = serde_json::to_string_pretty(&log)?;
     println!("{}", json);
     Ok(())
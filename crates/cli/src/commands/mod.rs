@@ -1,12 +1,62 @@
 //! CLI commands
 
+use revet_core::{MutationJournal, StoragePaths};
+use std::path::Path;
+use std::sync::Mutex;
+
+#[cfg(feature = "api")]
+pub mod api;
+pub mod audit;
 pub mod baseline;
+pub mod calibrate;
+pub mod ci;
 pub mod config_check;
+pub mod daemon;
+pub mod debug;
 pub mod diff;
 pub mod explain;
+pub mod fixes;
+pub mod hash_inputs;
+pub mod ignore;
+pub mod import;
 pub mod init;
+pub mod issues;
 pub mod log;
+pub mod multi;
 pub mod report;
 pub mod review;
+pub mod sbom;
+pub mod snapshot;
 pub mod stats;
+pub mod storage;
+pub mod trend;
+pub mod upgrade_report;
 pub mod watch;
+
+/// Command line a daemon is running for a client, in place of its own
+static COMMAND_LINE: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+/// Record `args` as the command line being run, until set back to `None`
+pub(crate) fn set_command_line(args: Option<Vec<String>>) {
+    *COMMAND_LINE.lock().unwrap_or_else(|e| e.into_inner()) = args;
+}
+
+/// The command line being run: the client's under `revet daemon`
+fn command_line() -> Vec<String> {
+    COMMAND_LINE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(|| std::env::args().collect())
+}
+
+/// The journal recording what the running command changes in `repo_root`,
+/// logging nothing with `no_audit` (`--no-audit`)
+pub fn journal(repo_root: &Path, paths: &StoragePaths, no_audit: bool) -> MutationJournal {
+    let journal = MutationJournal::new(paths, repo_root, command_line());
+    if no_audit {
+        journal.without_log()
+    } else {
+        journal
+    }
+}
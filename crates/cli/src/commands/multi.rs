@@ -0,0 +1,292 @@
+//! `revet multi` — review every repository of a workspace in parallel
+//!
+//! Repositories listed in `revet-workspace.toml` are reviewed by a bounded
+//! pool of workers, each with its own config and baseline. A failing repo
+//! (missing, not a git repository, invalid config) is reported in the summary
+//! table without stopping the others. Each worker keeps at most one code
+//! graph alive: [`review::analyze`] drops it before returning.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use revet_core::{RevetConfig, ReviewSummary, WorkspaceConfig, WorkspaceRepo, WORKSPACE_FILE};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::review::{self, ReviewRun};
+use crate::exit::{self, Classify, Outcome};
+use crate::output::{resolve_locale, resolve_targets, Pipeline, Reporter, RunEnd};
+use crate::progress;
+
+/// Result of reviewing one workspace repository
+pub struct RepoOutcome {
+    pub repo: WorkspaceRepo,
+    pub result: Result<ReviewRun>,
+    pub elapsed: Duration,
+}
+
+impl RepoOutcome {
+    /// Whether this repo fails the run: an error, or findings over its own
+    /// gate / fail-on threshold
+    pub fn failed(&self, cli: &crate::Cli) -> bool {
+        match &self.result {
+            Ok(review) => !review.outcome(cli).is_success(),
+            Err(_) => true,
+        }
+    }
+}
+
+/// A resolved workspace: its root directory and every repo outcome, in the
+/// order the workspace file lists them
+pub struct WorkspaceReview {
+    pub root: PathBuf,
+    pub outcomes: Vec<RepoOutcome>,
+}
+
+pub fn run(workspace: Option<&Path>, jobs: Option<usize>, cli: &crate::Cli) -> Result<Outcome> {
+    let start = Instant::now();
+
+    eprintln!(
+        "{}",
+        format!("  revet v{} — analyzing workspace", revet_core::VERSION)
+            .bold()
+            .yellow()
+    );
+    eprintln!();
+
+    let ws = analyze_workspace(workspace, jobs, cli)?;
+
+    // Output settings come from the workspace root's config, if any
+    let root_config = RevetConfig::find_and_load(&ws.root).usage_error()?;
+    let targets = resolve_targets(cli, &root_config)?;
+    let locale = resolve_locale(&root_config);
+
+    let mut out = Pipeline::new(&targets, &ws.root, cli.show_suppressed, cli.verbose, locale);
+    let mut total = ReviewSummary::default();
+    let mut suppressed = Vec::new();
+    for outcome in &ws.outcomes {
+        let Ok(review) = &outcome.result else {
+            continue;
+        };
+        let shown =
+            !review.findings.is_empty() || (cli.show_suppressed && !review.suppressed.is_empty());
+        if shown {
+            out.begin_repo(&outcome.repo.name, &review.repo_path);
+        }
+        for f in &review.findings {
+            out.report_finding(f, &review.repo_path);
+        }
+        if cli.show_suppressed {
+            for sf in &review.suppressed {
+                out.report_suppressed(sf, &review.repo_path);
+            }
+        }
+        merge_summary(&mut total, &review.summary);
+        suppressed.extend(review.suppressed.iter().cloned());
+    }
+    let failed = ws.outcomes.iter().filter(|o| o.failed(cli)).count();
+    out.end_run(&RunEnd {
+        elapsed: start.elapsed(),
+        passed: failed == 0,
+        ..RunEnd::new(&total, &suppressed)
+    });
+    out.finish(&root_config.output)?;
+
+    print_repo_table(&ws.outcomes, cli);
+
+    if failed > 0 {
+        Ok(Outcome::ReposFailed { count: failed })
+    } else {
+        Ok(Outcome::Success)
+    }
+}
+
+/// Resolve the workspace and review its repos with at most `jobs` (then the
+/// file's `jobs`, then available parallelism) running at once.
+///
+/// `workspace` is a workspace file or a directory containing
+/// `revet-workspace.toml` (default: the current directory).
+pub fn analyze_workspace(
+    workspace: Option<&Path>,
+    jobs: Option<usize>,
+    cli: &crate::Cli,
+) -> Result<WorkspaceReview> {
+    if cli.progressive.is_some() {
+        return Err(exit::usage(
+            "--progressive streams one repository; it is not supported by `revet multi`",
+        ));
+    }
+    if cli.explain_policy.is_some() {
+        return Err(exit::usage(
+            "--explain-policy explains one repository; run `revet review` in it instead",
+        ));
+    }
+    let file = workspace_file(workspace.unwrap_or_else(|| Path::new("."))).usage_error()?;
+    let config = WorkspaceConfig::from_file(&file).usage_error()?;
+    let root = file
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let repos = config.resolve_repos(&root).usage_error()?;
+    if repos.is_empty() {
+        return Err(exit::usage(format!(
+            "{} lists no repositories",
+            file.display()
+        )));
+    }
+
+    let jobs = jobs
+        .or(config.jobs)
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+        .clamp(1, repos.len());
+
+    eprintln!(
+        "  {} {} repositories, {} at a time",
+        "Reviewing".bold(),
+        repos.len(),
+        jobs
+    );
+
+    // Per-repo pipeline steps would interleave across workers
+    progress::set_quiet(true);
+    let next = AtomicUsize::new(0);
+    let slots: Vec<Mutex<Option<RepoOutcome>>> = repos.iter().map(|_| Mutex::new(None)).collect();
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(repo) = repos.get(i) else {
+                    break;
+                };
+                let started = Instant::now();
+                let result = review_repo(&repo.path, cli);
+                let outcome = RepoOutcome {
+                    repo: repo.clone(),
+                    result,
+                    elapsed: started.elapsed(),
+                };
+                eprintln!("  {}", status_line(&outcome, cli));
+                *slots[i].lock().unwrap_or_else(|e| e.into_inner()) = Some(outcome);
+            });
+        }
+    });
+    progress::set_quiet(false);
+    eprintln!();
+
+    let outcomes = slots
+        .into_iter()
+        .filter_map(|slot| slot.into_inner().unwrap_or_else(|e| e.into_inner()))
+        .collect();
+    Ok(WorkspaceReview { root, outcomes })
+}
+
+fn workspace_file(path: &Path) -> Result<PathBuf> {
+    let file = if path.is_dir() {
+        path.join(WORKSPACE_FILE)
+    } else {
+        path.to_path_buf()
+    };
+    std::fs::canonicalize(&file).with_context(|| format!("{} not found", file.display()))
+}
+
+/// Review one repo, turning a panic in the pipeline into an error so the
+/// other workers keep going
+fn review_repo(path: &Path, cli: &crate::Cli) -> Result<ReviewRun> {
+    if !path.is_dir() {
+        bail!("directory not found");
+    }
+    if !path.join(".git").exists() {
+        bail!("not a git repository");
+    }
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| review::analyze(path, cli)))
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("analysis panicked")))
+}
+
+fn status_line(outcome: &RepoOutcome, cli: &crate::Cli) -> String {
+    let name = &outcome.repo.name;
+    match &outcome.result {
+        Ok(review) if outcome.failed(cli) => format!(
+            "{} {} — {} finding(s)",
+            "\u{2717}".red(),
+            name,
+            review.findings.len()
+        ),
+        Ok(review) => format!(
+            "{} {} — {} finding(s)",
+            "\u{2713}".green(),
+            name,
+            review.findings.len()
+        ),
+        Err(e) => format!("{} {} — {:#}", "\u{2717}".red(), name, e),
+    }
+}
+
+pub(crate) fn merge_summary(total: &mut ReviewSummary, summary: &ReviewSummary) {
+    total.errors += summary.errors;
+    total.warnings += summary.warnings;
+    total.info += summary.info;
+    total.files_analyzed += summary.files_analyzed;
+    total.nodes_parsed += summary.nodes_parsed;
+    for (lang, count) in &summary.files_by_language {
+        *total.files_by_language.entry(lang.clone()).or_default() += count;
+    }
+}
+
+/// Per-repo counts, pass/fail and duration, on stderr so JSON/SARIF stdout
+/// stays machine-readable
+fn print_repo_table(outcomes: &[RepoOutcome], cli: &crate::Cli) {
+    let width = outcomes
+        .iter()
+        .map(|o| o.repo.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Repository".len());
+
+    eprintln!();
+    eprintln!(
+        "  {:<width$}  {:>6}  {:>8}  {:>5}  {:<6}  {:>7}",
+        "Repository".bold(),
+        "Errors",
+        "Warnings",
+        "Info",
+        "Result",
+        "Time",
+        width = width
+    );
+    eprintln!("  {}", "\u{2500}".repeat(width + 44).dimmed());
+    for o in outcomes {
+        let time = format!("{:.1}s", o.elapsed.as_secs_f64());
+        match &o.result {
+            Ok(review) => {
+                let result = if o.failed(cli) {
+                    "FAIL".red().bold()
+                } else {
+                    "PASS".green().bold()
+                };
+                eprintln!(
+                    "  {:<width$}  {:>6}  {:>8}  {:>5}  {:<6}  {:>7}",
+                    o.repo.name,
+                    review.summary.errors,
+                    review.summary.warnings,
+                    review.summary.info,
+                    result,
+                    time,
+                    width = width
+                );
+            }
+            Err(e) => eprintln!(
+                "  {:<width$}  {:>6}  {:>8}  {:>5}  {:<6}  {:>7}  {}",
+                o.repo.name,
+                "-",
+                "-",
+                "-",
+                "ERROR".red().bold(),
+                time,
+                format!("{:#}", e).dimmed(),
+                width = width
+            ),
+        }
+    }
+    eprintln!();
+}
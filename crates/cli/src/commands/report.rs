@@ -1,16 +1,19 @@
 //! `revet report --html` — generate a self-contained HTML quality report.
 //!
-//! Reads run history from `.revet-cache/runs/` and produces a portable HTML
+//! Reads run history from the state directory (`.revet-cache/runs/` by default) and produces a portable HTML
 //! file with CSS charts. No external dependencies — the file works offline.
 
 use anyhow::{bail, Result};
+use revet_core::analyzer::registry::prefix_of;
+use revet_core::StoragePaths;
 use std::collections::HashMap;
 use std::path::Path;
 
 use crate::run_log::{self, RunLog};
 
 pub fn run(repo_path: &Path, output: &str, last_n: Option<usize>) -> Result<()> {
-    let entries = run_log::list_runs(repo_path)?;
+    let paths = StoragePaths::discover(repo_path);
+    let entries = run_log::list_runs(&paths)?;
 
     if entries.is_empty() {
         bail!("No run history found. Run `revet review` first to build history.");
@@ -18,11 +21,11 @@ pub fn run(repo_path: &Path, output: &str, last_n: Option<usize>) -> Result<()>
 
     let mut logs: Vec<RunLog> = entries
         .iter()
-        .filter_map(|e| run_log::load_run_log(repo_path, &e.id).ok())
+        .filter_map(|e| run_log::load_run_log(&paths, &e.id).ok())
         .collect();
 
     // Newest first
-    logs.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    logs.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
     if let Some(n) = last_n {
         logs.truncate(n);
     }
@@ -65,13 +68,13 @@ impl<'a> ReportData<'a> {
         if let Some(latest) = latest {
             for f in &latest.findings {
                 if !f.suppressed {
-                    let prefix = f.id.split('-').next().unwrap_or(&f.id).to_string();
+                    let prefix = prefix_of(&f.id).to_string();
                     *rule_map.entry(prefix).or_default() += 1;
                 }
             }
         }
         let mut rule_counts: Vec<(String, usize)> = rule_map.into_iter().collect();
-        rule_counts.sort_by(|a, b| b.1.cmp(&a.1));
+        rule_counts.sort_by_key(|e| std::cmp::Reverse(e.1));
         rule_counts.truncate(10);
 
         // File counts from latest run's active findings
@@ -84,7 +87,7 @@ impl<'a> ReportData<'a> {
             }
         }
         let mut file_counts: Vec<(String, usize)> = file_map.into_iter().collect();
-        file_counts.sort_by(|a, b| b.1.cmp(&a.1));
+        file_counts.sort_by_key(|e| std::cmp::Reverse(e.1));
         file_counts.truncate(10);
 
         // Trend — last 14 runs, oldest first
@@ -1,35 +1,120 @@
 //! Main review command — wires parser, graph, impact analysis, and cache together
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::Colorize;
+use revet_core::ai::{is_eligible, AiReport, PriorityContext};
+use revet_core::analyzer::complexity::delta::DeltaBase;
+use revet_core::analyzer::docs;
+use revet_core::analyzer::ownership::codeowners::CodeOwners;
+use revet_core::analyzer::partition;
+use revet_core::analyzer::secret_exposure::SecretExposureAnalyzer;
+use revet_core::baseline::delta::{self, Delta, Snapshot};
+use revet_core::import;
+use revet_core::policy::{self, Action, Policy, Status};
+use revet_core::profile::FIRST_RUN_PROFILE;
+use revet_core::schedule::{self, Phase, Stage};
+use revet_core::suggestion::{self, SuggestionStats};
 use revet_core::{
-    apply_fixes, create_store, discover_files, discover_files_extended, filter_findings,
-    filter_findings_by_diff, filter_findings_by_inline, filter_findings_by_path_rules,
-    reconstruct_graph, AnalyzerDispatcher, AnalyzerTiming, Baseline, BlastRadiusSummary, CodeGraph,
-    DiffAnalyzer, FileGraphCache, Finding, GateConfig, GitTreeReader, GraphCache, GraphCacheMeta,
-    GraphStore, ImpactAnalysis, ParserDispatcher, RevetConfig, ReviewSummary, Severity,
-    SuppressedFinding,
+    analyze_buffer, apply_fixes, create_store_at, discover_files, discover_files_recorded,
+    discover_files_with_dotfiles, filter_findings, filter_findings_by_diff,
+    filter_findings_by_path_rules, filter_inline, filter_inline_with, is_dotfile, matches_dotfile,
+    open_store, reconstruct_graph, AnalysisCache, AnalysisMemo, AnalyzerDispatcher, AnalyzerTiming,
+    Anchor, Baseline, BaselineEntry, BlastRadiusSummary, CacheFormat, CodeGraph, CodeKinds,
+    Constructs, CoverageLedger, DiffAnalyzer, FileGraphCache, Finding, FixReport, GateConfig,
+    GeneratedFiles, GeneratedSet, GitObjects, GitTreeReader, GraphCache, GraphCacheMeta,
+    GraphContext, GraphStore, ImpactAnalysis, Locale, MemoStats, MemoryGuard, ParserDispatcher,
+    PolicyReport, ProfileSettings, Project, RemoteStats, RevetConfig, ReviewEstimate,
+    ReviewSummary, RiskReport, Rollout, Scheduler, Scope, Severity, ShadowModule, Shedding,
+    SkipReason, StoragePaths, SuppressedFinding, TimingReport, Timings, VendoredFiles, VendoredSet,
 };
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::time::{Instant, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 
 use crate::ai::AiReasoner;
+use crate::exit::{self, Classify, Outcome};
+use crate::output::chunks::Written;
 use crate::output::github_comment;
-use crate::output::{make_formatter, resolve_format};
+use crate::output::progressive::ProgressiveOutput;
+use crate::output::{
+    job_summary, resolve_locale, resolve_targets, Format, Pipeline, Reporter, RunEnd, RunStart,
+    Target,
+};
 use crate::progress::Step;
-use crate::run_log;
+use crate::remote_cache;
+use crate::run_log::{self, BaselineRecord, RunScope};
+use crate::webhook::{self, DeltaEvent};
+use crate::{CoverageDetail, ProgressiveFormat, TimingsDetail};
+
+/// Commits walked by the history secret scan (`deep` profile)
+const HISTORY_SCAN_COMMITS: usize = 500;
 
-/// Exit status from the review command
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ReviewExitCode {
-    /// No findings exceeding the configured threshold
-    Success,
-    /// Findings exceeded the configured threshold
-    FindingsExceedThreshold,
+/// Results of the review pipeline for one repository, ready for output.
+///
+/// Holds no code graph: graphs are dropped before [`analyze`] returns, so
+/// callers reviewing many repositories only keep findings in memory.
+pub struct ReviewRun {
+    pub repo_path: PathBuf,
+    pub config: RevetConfig,
+    pub settings: ProfileSettings,
+    pub findings: Vec<Finding>,
+    pub suppressed: Vec<SuppressedFinding>,
+    /// Findings below the profile's confidence or severity floor, or
+    /// outside `--only`
+    pub dropped: usize,
+    pub summary: ReviewSummary,
+    /// `[[policy]]` results over the findings and suppressed findings
+    pub policies: PolicyReport,
+    pub blast_radius: Option<BlastRadiusSummary>,
+    pub risk: Option<RiskReport>,
+    /// Review effort and suggested reviewers, in diff mode
+    pub review_estimate: Option<ReviewEstimate>,
+    pub ledger: Option<CoverageLedger>,
+    /// AI cost estimate and spend, when `--ai` ran
+    pub ai: Option<AiReport>,
+    /// Run log ID, when the log was saved
+    pub run_id: Option<String>,
+    pub domain_timings: Vec<AnalyzerTiming>,
+    pub graph_timings: Vec<AnalyzerTiming>,
+    /// Code suggestions checked and demoted to prose, by rule
+    pub suggestions: SuggestionStats,
+    /// Which `rollout_percent` modules reported findings and which ran in
+    /// shadow mode
+    pub rollout: Rollout,
+    pub elapsed: Duration,
 }
 
-pub fn run(path: Option<&Path>, cli: &crate::Cli) -> Result<ReviewExitCode> {
-    let start = Instant::now();
+impl ReviewRun {
+    /// Whether no files matched discovery (nothing was analyzed)
+    pub fn is_empty(&self) -> bool {
+        self.summary.files_analyzed == 0
+    }
+
+    /// Exit status: the quality gate (`--gate`, then `[gate]`) takes
+    /// precedence over the fail-on threshold; a violated `fail` policy
+    /// fails the review either way. Nothing analyzed always succeeds.
+    pub fn outcome(&self, cli: &crate::Cli) -> Outcome {
+        if self.is_empty() {
+            return Outcome::Success;
+        }
+        let gate = cli
+            .gate
+            .as_deref()
+            .map(GateConfig::from_flag)
+            .unwrap_or_else(|| self.config.gate.clone());
+
+        let outcome = Outcome::from_threshold(&self.summary, &gate, &self.settings.fail_on);
+        if outcome.is_success() && self.policies.fails() {
+            return Outcome::PolicyFailed {
+                policies: self.policies.failed().map(|p| p.name.clone()).collect(),
+            };
+        }
+        outcome
+    }
+}
+
+pub fn run(path: Option<&Path>, cli: &crate::Cli) -> Result<Outcome> {
     let repo_path = path.unwrap_or_else(|| Path::new("."));
     let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
@@ -41,18 +126,397 @@ pub fn run(path: Option<&Path>, cli: &crate::Cli) -> Result<ReviewExitCode> {
     );
     eprintln!();
 
+    // A task runner's cached result is only trusted while the project's
+    // inputs are the ones it was cached under
+    if let Some(expected) = &cli.assert_hash {
+        if let Some(stale) = super::hash_inputs::check(&repo_path, cli, expected)? {
+            return Ok(stale);
+        }
+    }
+
+    let review = analyze(&repo_path, cli)?;
+    let targets = resolve_targets(cli, &review.config)?;
+    output(&review, &targets, cli)?;
+    if cli.github_job_summary {
+        write_job_summary(&review, cli);
+    }
+
+    // The findings above are partial: fail as the environment's limit
+    if let Some(memory) = review.summary.memory.as_ref().filter(|m| m.exceeded) {
+        return Err(exit::environment(format!(
+            "memory use exceeded [performance] max_memory_mb = {} before {}; findings cover only the stages before it",
+            memory.max_memory_mb,
+            memory.stopped_before.as_deref().unwrap_or("the end of the review")
+        )));
+    }
+
+    Ok(review.outcome(cli))
+}
+
+/// Review the content piped to stdin as the repository file `file`
+/// (`revet review --stdin --path FILE`), for editor and hook pipelines that
+/// have no checkout of it.
+///
+/// The rest of the repository, as found on disk, provides the graph
+/// context, read through the parse cache unless `--no-cache`; nothing is
+/// written back (no fixes, cache fragments or run log). Findings
+/// carry `file`, so per-path rules, the baseline and session ignores apply
+/// as in a full review, and `--fail-on` and the gate decide the exit code.
+pub fn run_stdin(path: Option<&Path>, file: &Path, cli: &crate::Cli) -> Result<Outcome> {
+    let start = Instant::now();
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+    if cli.fix {
+        return Err(exit::usage(
+            "--fix can't be combined with --stdin: there is no file to fix",
+        ));
+    }
+    let file = if file.is_absolute() {
+        file.to_path_buf()
+    } else {
+        repo_path.join(file)
+    };
+    if !file.starts_with(&repo_path) {
+        return Err(exit::usage(format!(
+            "--path {} is outside the repository {}",
+            file.display(),
+            repo_path.display()
+        )));
+    }
+
+    eprintln!(
+        "{}",
+        format!(
+            "  revet v{} — analyzing stdin as {}",
+            revet_core::VERSION,
+            file.strip_prefix(&repo_path).unwrap_or(&file).display()
+        )
+        .bold()
+        .yellow()
+    );
+    eprintln!();
+
+    let mut content = String::new();
+    std::io::stdin()
+        .read_to_string(&mut content)
+        .context("Failed to read file content from stdin")?;
+
+    let mut config = RevetConfig::find_and_load(&repo_path).usage_error()?;
+    let settings = crate::profile::resolve_review(cli, &repo_path, &mut config).usage_error()?;
+    let policies = compile_policies(&config, cli).usage_error()?;
+    let targets = resolve_targets(cli, &config)?;
+    let paths = StoragePaths::resolve(&repo_path, &config);
+
+    let step = Step::new("Analyzing buffer");
+    let file_cache = FileGraphCache::at(&paths)
+        .enabled(!cli.no_cache)
+        .read_only(true);
+    let ctx = GraphContext::load_with(&repo_path, &config, &file_cache)?;
+    let mut findings = analyze_buffer(&file, &content, &config, &ctx);
+    let node_count = ctx.graph().nodes().count();
+    drop(ctx);
+    step.finish(&format!("{} finding(s)", findings.len()));
+
+    let before = findings.len();
+    findings.retain(|f| settings.keeps(f));
+    let dropped = before - findings.len();
+
+    let mut suppressed = Vec::new();
+    if !config.ignore.per_path.is_empty() {
+        let (kept, path_suppressed) =
+            filter_findings_by_path_rules(findings, &config.ignore.per_path, &repo_path);
+        findings = kept;
+        suppressed.extend(path_suppressed);
+    }
+    if !cli.no_baseline {
+        if let Some(baseline) = Baseline::load_at(&paths)? {
+            let (kept, baseline_suppressed) = filter_findings(findings, &baseline, &repo_path);
+            findings = kept;
+            suppressed.extend(baseline_suppressed);
+        }
+    }
+    let session_ignored = super::ignore::active(cli, &paths)
+        .map(|ignores| super::ignore::apply(&ignores, &mut findings, &mut suppressed, &repo_path));
+    let policies = policy::evaluate(&policies, &findings, &suppressed, &repo_path);
+
+    let mut summary = build_summary(&findings, std::slice::from_ref(&file), node_count);
+    summary.session_ignored = session_ignored;
+    let review = ReviewRun {
+        policies,
+        rollout: Rollout::resolve(&config, &repo_path, settings.rollout_sampling),
+        repo_path,
+        config,
+        settings,
+        findings,
+        suppressed,
+        dropped,
+        summary,
+        blast_radius: None,
+        risk: None,
+        review_estimate: None,
+        ledger: None,
+        ai: None,
+        run_id: None,
+        domain_timings: Vec::new(),
+        graph_timings: Vec::new(),
+        suggestions: SuggestionStats::default(),
+        elapsed: start.elapsed(),
+    };
+    output(&review, &targets, cli)?;
+    Ok(review.outcome(cli))
+}
+
+/// Report a review to every target: PR comments (`--post-comment`),
+/// findings, coverage, summary and timings. Returns the JSON and SARIF
+/// report files written, per format.
+pub fn output(
+    review: &ReviewRun,
+    targets: &[Target],
+    cli: &crate::Cli,
+) -> Result<Vec<(Format, Written)>> {
+    let repo_path = &review.repo_path;
+    let locale = resolve_locale(&review.config);
+
+    if let Some(name) = &cli.explain_policy {
+        explain_policy(review, name, locale);
+        return Ok(Vec::new());
+    }
+
+    let coverage = match (&review.ledger, cli.coverage_report) {
+        (Some(ledger), Some(detail)) => {
+            let mut report = ledger.report(repo_path, detail == CoverageDetail::Full);
+            report.degraded = review.summary.degraded.clone();
+            Some(report)
+        }
+        _ => None,
+    };
+    if review.is_empty() {
+        let mut out = Pipeline::new(targets, repo_path, false, false, locale);
+        out.end_run(&RunEnd {
+            coverage: coverage.as_ref(),
+            ..RunEnd::no_files(review.elapsed)
+        });
+        return out.finish(&review.config.output);
+    }
+
+    // ── Post GitHub PR comments ──────────────────────────────────
+    if cli.post_comment {
+        post_github_comments(
+            &review.findings,
+            review.risk.as_ref(),
+            repo_path,
+            &review.settings.diff_base,
+            locale,
+        );
+    }
+
+    // ── Output ───────────────────────────────────────────────────
+    let mut out = Pipeline::new(targets, repo_path, cli.show_suppressed, cli.verbose, locale);
+    out.begin_run(&RunStart {
+        reproducibility: Some(
+            &review
+                .settings
+                .reproducibility()
+                .with_rollout(&review.rollout)
+                .with_language_targets(repo_path, &review.config),
+        ),
+        blast_radius: review.blast_radius.as_ref(),
+        risk: review.risk.as_ref(),
+        review_estimate: review.review_estimate.as_ref(),
+        ai: review.ai.as_ref(),
+    });
+    // `max_per_rule` caps the findings shown; the summary counts them all
+    let mut per_rule: HashMap<String, usize> = HashMap::new();
+    let mut capped = 0;
+    for f in &review.findings {
+        if let Some(max) = review.settings.max_per_rule {
+            let shown = per_rule.entry(suggestion::rule_of(f)).or_default();
+            if *shown >= max {
+                capped += 1;
+                continue;
+            }
+            *shown += 1;
+        }
+        out.report_finding(f, repo_path);
+    }
+    if cli.show_suppressed {
+        for sf in &review.suppressed {
+            out.report_suppressed(sf, repo_path);
+        }
+    }
+    out.end_run(&RunEnd {
+        policies: (!review.policies.is_empty()).then_some(&review.policies),
+        coverage: coverage.as_ref(),
+        elapsed: review.elapsed,
+        run_id: review.run_id.as_deref(),
+        passed: review.outcome(cli).is_success(),
+        ..RunEnd::new(&review.summary, &review.suppressed)
+    });
+    let written = out.finish(&review.config.output)?;
+
+    if review.settings.name == FIRST_RUN_PROFILE {
+        print_next_steps(review, capped);
+    } else if capped > 0 {
+        eprintln!(
+            "  {} more findings not shown (max_per_rule = {})",
+            capped,
+            review.settings.max_per_rule.unwrap_or_default()
+        );
+    }
+
+    // ── Timings (optional) ───────────────────────────────────────
+    if let Some(detail) = cli.timings {
+        print_timings(&review.domain_timings, &review.graph_timings);
+        if let Some(stats) = review.summary.timings.as_ref().and_then(|t| t.remote_cache) {
+            print_remote_cache(&stats);
+        }
+        if let Some(stats) = review.summary.timings.as_ref().and_then(|t| t.memo) {
+            print_memo(&stats);
+        }
+        if detail == TimingsDetail::Full {
+            print_slowest_files(
+                &review.domain_timings,
+                &review.graph_timings,
+                &review.repo_path,
+            );
+        }
+    }
+    if cli.show_rule_stats {
+        print_rule_stats(
+            &review.findings,
+            &review.suggestions,
+            &review.summary.shadow,
+        );
+    }
+    Ok(written)
+}
+
+/// What a first run left out, and how to configure the next one
+fn print_next_steps(review: &ReviewRun, capped: usize) {
+    eprintln!();
+    eprintln!("  {}", "Next steps".bold());
+    eprintln!(
+        "  No .revet.toml was found, so only high-confidence security, complexity and unused-export checks ran."
+    );
+    let mut hidden = Vec::new();
+    if capped > 0 {
+        hidden.push(format!(
+            "{} beyond {} per rule",
+            capped,
+            review.settings.max_per_rule.unwrap_or_default()
+        ));
+    }
+    if review.dropped > 0 {
+        hidden.push(format!(
+            "{} below the confidence or severity floor",
+            review.dropped
+        ));
+    }
+    if !hidden.is_empty() {
+        eprintln!(
+            "  {} findings were hidden by first-run defaults ({}).",
+            capped + review.dropped,
+            hidden.join(", ")
+        );
+    }
+    eprintln!(
+        "  Run {} to configure, or {} to adopt incrementally.",
+        "revet init".cyan(),
+        "revet baseline".cyan()
+    );
+    let suggestions = super::init::suggestions(&review.repo_path).unwrap_or_default();
+    if !suggestions.is_empty() {
+        eprintln!("  For this repository, revet init would also enable:");
+        for suggestion in &suggestions {
+            eprintln!(
+                "    {:<16} {}",
+                suggestion.module,
+                suggestion.languages.join(", ").dimmed()
+            );
+        }
+    }
+}
+
+/// Append the markdown report to the GitHub Actions job summary; a missing
+/// `$GITHUB_STEP_SUMMARY` is a warning, not a failed review
+pub fn write_job_summary(review: &ReviewRun, cli: &crate::Cli) {
+    let passed = review.outcome(cli).is_success();
+    let markdown = job_summary::render(
+        &review.findings,
+        &review.summary,
+        review.suppressed.len(),
+        &review.policies,
+        &review.repo_path,
+        passed,
+        resolve_locale(&review.config),
+    );
+    if let Err(e) = job_summary::append(&markdown) {
+        eprintln!("  {}: job summary not written: {:#}", "warn".yellow(), e);
+    }
+}
+
+/// Run the review pipeline on one repository: config, discovery, parsing,
+/// impact analysis, analyzers, suppression, cache and run log.
+///
+/// Prints progress steps but no findings; see [`run`] for output.
+pub fn analyze(repo_path: &Path, cli: &crate::Cli) -> Result<ReviewRun> {
+    analyze_with_imports(repo_path, cli, Vec::new())
+}
+
+/// [`analyze`] with findings of external tools (`revet import`) merged in
+/// after the analyzers. In diff-based runs only those in reviewed files
+/// are kept.
+pub fn analyze_with_imports(
+    repo_path: &Path,
+    cli: &crate::Cli,
+    imported: Vec<Finding>,
+) -> Result<ReviewRun> {
+    let start = Instant::now();
+    let repo_path = repo_path.to_path_buf();
+
     // ── 1. Config ────────────────────────────────────────────────
-    let config = RevetConfig::find_and_load(&repo_path)?;
-    let format = resolve_format(cli, &config);
+    let mut config = RevetConfig::find_and_load(&repo_path).usage_error()?;
+    let settings = crate::profile::resolve_review(cli, &repo_path, &mut config).usage_error()?;
+    if cli.estimate_only && !settings.ai {
+        return Err(exit::usage(
+            "--estimate-only needs --ai (or a profile with ai = true)",
+        ));
+    }
+    if cli.progressive == Some(ProgressiveFormat::Github)
+        && resolve_targets(cli, &config)?
+            .iter()
+            .any(|t| matches!(t.format, Format::Github | Format::GithubMatcher))
+    {
+        return Err(exit::usage(PROGRESSIVE_GITHUB_TWICE));
+    }
+    let policies = compile_policies(&config, cli).usage_error()?;
+    let project = cli
+        .project
+        .as_deref()
+        .map(|dir| Project::resolve(&repo_path, dir, &config))
+        .transpose()
+        .usage_error()?;
+    let paths = StoragePaths::resolve(&repo_path, &config);
+    let remote = remote_cache::open(&config.cache.remote, &repo_path);
+    // Partial clones fetch (or skip) what they lack under `[git]`
+    let git_objects =
+        GitObjects::open(&repo_path, &config.git).with_sparse_excluded(cli.include_sparse_excluded);
 
     // ── 2. File Discovery ────────────────────────────────────────
-    let dispatcher = ParserDispatcher::new();
-    let analyzer_dispatcher = AnalyzerDispatcher::new_with_config(&config);
+    let dispatcher = ParserDispatcher::new_with_config(&config);
+    let rollout = Rollout::resolve(&config, &repo_path, settings.rollout_sampling);
+    let analyzer_dispatcher = AnalyzerDispatcher::new_with_config(&config)
+        .with_file_timings(cli.timings == Some(TimingsDetail::Full))
+        .with_rollout(rollout)
+        .with_git_objects(git_objects.clone())
+        .with_memo(AnalysisMemo::session());
+    let memo_before = analyzer_dispatcher.memo().map(AnalysisMemo::stats);
     let extensions = dispatcher.supported_extensions();
 
     // Merge parser extensions with analyzer-specific extensions
     let extra_exts = analyzer_dispatcher.extra_extensions(&config);
     let extra_names = analyzer_dispatcher.extra_filenames(&config);
+    let extra_dotfiles = analyzer_dispatcher.extra_dotfiles(&config);
     let mut all_extensions: Vec<&str> = extensions.clone();
     for ext in &extra_exts {
         if !all_extensions.contains(ext) {
@@ -60,38 +524,357 @@ pub fn run(path: Option<&Path>, cli: &crate::Cli) -> Result<ReviewExitCode> {
         }
     }
 
-    let files = discover_review_files(&repo_path, cli, &config, &all_extensions, &extra_names)?;
+    let mut ledger = cli.coverage_report.map(|_| CoverageLedger::new());
+    // With --changed-since, everything discovery finds is kept as context
+    // for the graph
+    let (files, diff_based, context) = match cli.changed_since.as_deref() {
+        Some(rev) => {
+            let (changed, discovered) = changed_since_files(
+                &repo_path,
+                rev,
+                &config,
+                &all_extensions,
+                &extra_names,
+                &extra_dotfiles,
+                &git_objects,
+                ledger.as_mut(),
+            )?;
+            (changed, true, Some(discovered))
+        }
+        None => {
+            let (files, diff_based) = discover_review_files(
+                &repo_path,
+                &settings,
+                &config,
+                &all_extensions,
+                &extra_names,
+                &extra_dotfiles,
+                &git_objects,
+                ledger.as_mut(),
+            )?;
+            (files, diff_based, None)
+        }
+    };
+    // A sparse checkout's excluded paths count as not checked out
+    let mut files = git_objects.retain_sparse(&repo_path, files, ledger.as_mut());
+    let mut context = context.map(|context| git_objects.retain_sparse(&repo_path, context, None));
+    // With --project, the project and the projects it depends on
+    if let Some(project) = &project {
+        files.retain(|f| project.covers(&repo_path, f));
+        if let Some(context) = context.as_mut() {
+            context.retain(|f| project.covers(&repo_path, f));
+        }
+    }
+    let changed_since: Option<HashSet<PathBuf>> =
+        context.as_ref().map(|_| files.iter().cloned().collect());
+
+    // Changes to revet's own configuration, in diff-based runs, including
+    // those that fell back to a full scan because only config changed
+    let drift = if settings.scope != Scope::Full {
+        super::diff::config_drift(
+            cli,
+            &repo_path,
+            &paths,
+            &analyzer_dispatcher,
+            &config,
+            &settings.diff_base,
+            None,
+        )
+    } else {
+        Vec::new()
+    };
 
     if files.is_empty() {
-        let mut out = make_formatter(format, &repo_path, false);
-        out.write_no_files(start.elapsed());
-        out.finalize();
-        return Ok(ReviewExitCode::Success);
+        let mut summary = if drift.is_empty() {
+            ReviewSummary::default()
+        } else {
+            build_summary(&drift, &files, 0)
+        };
+        summary.degraded = git_objects.degradations();
+        return Ok(ReviewRun {
+            policies: policy::evaluate(&policies, &drift, &[], &repo_path),
+            repo_path,
+            config,
+            settings,
+            findings: drift,
+            suppressed: Vec::new(),
+            dropped: 0,
+            summary,
+            blast_radius: None,
+            risk: None,
+            review_estimate: None,
+            ledger,
+            ai: None,
+            run_id: None,
+            domain_timings: Vec::new(),
+            graph_timings: Vec::new(),
+            suggestions: SuggestionStats::default(),
+            rollout: analyzer_dispatcher.rollout().clone(),
+            elapsed: start.elapsed(),
+        });
+    }
+
+    // Generated files are parsed so other files resolve their symbols, but
+    // under `[files] generated = "skip"` no analyzer scans them
+    let generated = GeneratedFiles::from_config(&config.files).classify(&files);
+    // Vendored files matching their upstream aren't scanned either
+    let vendored = classify_vendored(&config, &repo_path, &repo_path, &files);
+    let analysis_files = vendored.analysis_files(&generated.analysis_files(&files));
+    let code_kinds = CodeKinds::from_config(&config.files);
+
+    // Loaded up front: progressive output filters each partition with it
+    let mut baseline = if cli.no_baseline {
+        None
+    } else {
+        Baseline::load_at(&paths)?
+    };
+
+    // Stages run cheapest first; with --time-budget, phases whose estimate
+    // no longer fits the time left are skipped
+    let mut scheduler = Scheduler::new(cli.time_budget, Timings::load(&paths), files.len());
+    let (mut content_config, deferred_scans) =
+        scheduler.admit_content(&analyzer_dispatcher.modules(&config), &config);
+
+    // With `[performance] max_memory_mb`, load is shed at each stage
+    // boundary once memory runs high, and the review stops over the ceiling
+    let mut memory = MemoryGuard::from_config(&config.performance);
+    if memory.as_ref().is_some_and(|m| !m.is_enforced()) {
+        eprintln!(
+            "  {}: [performance] max_memory_mb is not enforced: memory use can't be read on this platform",
+            "warn".yellow()
+        );
     }
+    let mut shed = Shed::default();
+    let delta_requested = (cli.complexity_delta || config.analyzers.complexity.mode == "delta")
+        && config.modules.complexity;
+    // The base-tree contents of the diff, read before the graph analyzers
+    let reads_base = settings.graph && diff_based && (delta_requested || config.modules.refactor);
+    let graph_phases = |config: &RevetConfig| {
+        let mut phases: Vec<Phase> = analyzer_dispatcher
+            .graph_modules(config)
+            .into_iter()
+            .map(|module| Phase::new(module, Stage::GraphAnalyzers))
+            .collect();
+        if reads_base && config.modules.refactor {
+            phases.push(Phase::new("refactor", Stage::GraphAnalyzers));
+        }
+        phases
+    };
+
+    // ── 2b. Domain analyzers ─────────────────────────────────────
+    // Domain analyzers don't need the graph, so they run first. With
+    // --progressive the per-file ones run one directory partition at a
+    // time, and each partition's findings are written as soon as it is done
+    let mut progressive = cli
+        .progressive
+        .map(|format| ProgressiveOutput::stderr(format, &repo_path, resolve_locale(&config)));
+    let mut remaining: Vec<Phase> = analyzer_dispatcher
+        .modules(&content_config)
+        .into_iter()
+        .map(|module| Phase::new(module, Stage::Content))
+        .collect();
+    remaining.extend(graph_phases(&config));
+    remaining.extend(scan_phases(
+        &deferred_scans,
+        settings.graph,
+        &config,
+        &settings,
+    ));
+    let mut stopped = memory_checkpoint(&mut memory, "domain analyzers", |step| {
+        shed.take(
+            step,
+            progressive.is_none(),
+            reads_base,
+            &remaining,
+            &scheduler,
+        )
+    });
+    content_config = shed.switch_off(&content_config);
+    let (domain_findings, mut domain_timings) = if stopped {
+        (Vec::new(), Vec::new())
+    } else if let Some(out) = progressive.as_mut() {
+        let partitions = partition::partition(&analysis_files, &repo_path);
+        out.begin_partitions(
+            partitions.len(),
+            &analyzer_dispatcher.whole_repo_analyzers(&config, settings.graph),
+        );
+        let step = Step::new(format!(
+            "Running domain analyzers ({} partition(s))",
+            partitions.len()
+        ));
+        let analyzer_start = Instant::now();
+        let mut index = 0;
+        let (analyzer_findings, timings) = analyzer_dispatcher.run_all_partitioned_timed(
+            &analysis_files,
+            &partitions,
+            &repo_path,
+            &content_config,
+            |partition, findings| {
+                index += 1;
+                let findings = settled(
+                    analyzer_dispatcher.split_shadow(findings).0,
+                    &generated,
+                    &vendored,
+                    &code_kinds,
+                    &settings,
+                    &config,
+                    baseline.as_ref(),
+                    &repo_path,
+                );
+                out.report_partition(index, partition, &findings);
+            },
+        );
+        step.finish(&format!(
+            "{} finding(s) ({:.1}s)",
+            analyzer_findings.len(),
+            analyzer_start.elapsed().as_secs_f64()
+        ));
+        (analyzer_findings, timings)
+    } else if shed.small_batches {
+        let partitions = partition::partition(&analysis_files, &repo_path);
+        let step = Step::new(format!(
+            "Running domain analyzers ({} partition(s), memory ceiling)",
+            partitions.len()
+        ));
+        let analyzer_start = Instant::now();
+        let (analyzer_findings, timings) = analyzer_dispatcher.run_all_partitioned_timed(
+            &analysis_files,
+            &partitions,
+            &repo_path,
+            &content_config,
+            |_, _| {},
+        );
+        step.finish(&format!(
+            "{} finding(s) ({:.1}s)",
+            analyzer_findings.len(),
+            analyzer_start.elapsed().as_secs_f64()
+        ));
+        (analyzer_findings, timings)
+    } else {
+        let step = Step::new("Running domain analyzers");
+        let analyzer_start = Instant::now();
+        // Shared by CI jobs reviewing the same files with the same config
+        let analysis_cache = remote.clone().map(|r| AnalysisCache::at(&paths, r));
+        let fingerprint = analysis_cache
+            .as_ref()
+            .and_then(|_| AnalysisCache::fingerprint(&analysis_files, &repo_path, &content_config));
+        let cached = analysis_cache.as_ref().zip(fingerprint.as_deref());
+        match cached.and_then(|(cache, key)| cache.load(key)) {
+            Some(analyzer_findings) => {
+                step.finish(&format!("{} finding(s) (cached)", analyzer_findings.len()));
+                (analyzer_findings, Vec::new())
+            }
+            None => {
+                let (analyzer_findings, timings) = analyzer_dispatcher.run_all_parallel_timed(
+                    &analysis_files,
+                    &repo_path,
+                    &content_config,
+                );
+                if let Some((cache, key)) = cached {
+                    cache.save(key, &analyzer_findings);
+                }
+                step.finish(&format!(
+                    "{} finding(s) ({:.1}s)",
+                    analyzer_findings.len(),
+                    analyzer_start.elapsed().as_secs_f64()
+                ));
+                (analyzer_findings, timings)
+            }
+        }
+    };
+    record_timings(&mut scheduler, &analyzer_dispatcher, &domain_timings);
 
     // ── 3. Parse (incremental, cache-aware) ──────────────────────
     let step = Step::new("Building code graph");
     let graph_start = Instant::now();
 
-    let file_cache = FileGraphCache::new(&repo_path);
-    let (graph, parse_errors, cached_count, parsed_count) =
-        dispatcher.parse_files_incremental(&files, repo_path.clone(), &file_cache);
-
+    let graph_phase = Phase::new(schedule::GRAPH, Stage::Graph);
+    if settings.graph {
+        let active = shed.switch_off(&config);
+        let mut remaining = graph_phases(&active);
+        remaining.extend(scan_phases(&deferred_scans, true, &active, &settings));
+        stopped = memory_checkpoint(&mut memory, "code graph", |step| {
+            shed.take(step, false, reads_base, &remaining, &scheduler)
+        });
+    }
+    let build_graph = settings.graph && !stopped && scheduler.admit(&graph_phase);
+    let (graph, parse_errors) = if build_graph {
+        let file_cache = FileGraphCache::at(&paths)
+            .with_remote(remote.clone())
+            .enabled(!cli.no_cache);
+        let parsed_files = context.as_deref().unwrap_or(&files);
+        let (graph, mut parse_errors, cached_count, parsed_count) =
+            dispatcher.parse_files_incremental(parsed_files, repo_path.clone(), &file_cache);
+        // Only a parse of the whole tree knows which fragments are unused
+        if (context.is_some() || !diff_based) && project.is_none() {
+            file_cache.evict_unused();
+        }
+        // With --changed-since, the changed files and those one import or
+        // call away, so that cross-file analyzers see both ends
+        let graph = match &changed_since {
+            Some(changed) => {
+                parse_errors.retain(|error| {
+                    changed
+                        .iter()
+                        .any(|f| error.starts_with(&format!("{}:", f.display())))
+                });
+                graph.restricted_to(&graph.query().adjacent_files(changed))
+            }
+            None => graph,
+        };
+        step.finish(&format!(
+            "{} files ({} cached, {} parsed), {} nodes ({:.1}s)",
+            parsed_files.len(),
+            cached_count,
+            parsed_count,
+            graph.nodes().count(),
+            graph_start.elapsed().as_secs_f64()
+        ));
+        scheduler.record(schedule::GRAPH, graph_start.elapsed());
+        (graph, parse_errors)
+    } else if settings.graph && stopped {
+        step.skip("Skipped (memory ceiling)");
+        (CodeGraph::new(repo_path.clone()), Vec::new())
+    } else if settings.graph {
+        step.skip("Skipped (time budget)");
+        for module in analyzer_dispatcher.graph_modules(&config) {
+            scheduler.skip_dependent(&Phase::new(module, Stage::GraphAnalyzers), &graph_phase);
+        }
+        if config.modules.hotspots {
+            scheduler.skip_dependent(&Phase::new("hotspots", Stage::Scans), &graph_phase);
+        }
+        (CodeGraph::new(repo_path.clone()), Vec::new())
+    } else {
+        step.skip(&format!("Skipped ({} profile)", settings.name));
+        (CodeGraph::new(repo_path.clone()), Vec::new())
+    };
     let node_count: usize = graph.nodes().count();
-    step.finish(&format!(
-        "{} files ({} cached, {} parsed), {} nodes ({:.1}s)",
-        files.len(),
-        cached_count,
-        parsed_count,
-        node_count,
-        graph_start.elapsed().as_secs_f64()
-    ));
+    if let Some(ledger) = ledger.as_mut() {
+        if build_graph {
+            dispatcher.record_coverage(&files, &parse_errors, ledger);
+        }
+        analyzer_dispatcher.record_coverage(&analysis_files, &content_config, ledger);
+        generated.record(ledger);
+        vendored.record(ledger);
+    }
 
     // ── 4. Impact Analysis ───────────────────────────────────────
     let mut findings: Vec<Finding> = Vec::new();
     let mut blast_radius: Option<BlastRadiusSummary> = None;
+    let mut risk: Option<RiskReport> = None;
 
-    let old_graph = load_old_graph(&repo_path, cli, &config, &dispatcher);
+    let old_graph = if build_graph {
+        load_old_graph(
+            &repo_path,
+            &paths,
+            &settings,
+            &config,
+            &dispatcher,
+            &git_objects,
+        )
+    } else {
+        None
+    };
 
     if let Some(baseline) = old_graph {
         let step = Step::new("Running impact analysis");
@@ -107,6 +890,12 @@ pub fn run(path: Option<&Path>, cli: &crate::Cli) -> Result<ReviewExitCode> {
             analysis.new_graph(),
             &repo_path,
         ));
+        risk = Some(RiskReport::compute(
+            &report,
+            analysis.new_graph(),
+            &repo_path,
+            &config.risk,
+        ));
 
         for change in &report.changes {
             let severity = match change.classification {
@@ -395,210 +1184,942 @@ pub fn run(path: Option<&Path>, cli: &crate::Cli) -> Result<ReviewExitCode> {
     }
 
     // ── 4b. Domain Analyzers ─────────────────────────────────────
-    let step = Step::new("Running domain analyzers");
-    let analyzer_start = Instant::now();
-    let (analyzer_findings, domain_timings) =
-        analyzer_dispatcher.run_all_parallel_timed(&files, &repo_path, &config);
-    let analyzer_count = analyzer_findings.len();
-    findings.extend(analyzer_findings);
-    step.finish(&format!(
-        "{} finding(s) ({:.1}s)",
-        analyzer_count,
-        analyzer_start.elapsed().as_secs_f64()
-    ));
+    findings.extend(domain_findings);
 
     // ── 4b'. Graph analyzers ─────────────────────────────────────────
-    let step = Step::new("Running graph analyzers");
-    let ga_start = Instant::now();
-    let (graph_findings, graph_timings) =
-        analyzer_dispatcher.run_graph_analyzers_timed(&graph, &config);
-    let graph_count = graph_findings.len();
-    findings.extend(graph_findings);
-    step.finish(&format!(
-        "{} finding(s) ({:.1}s)",
-        graph_count,
-        ga_start.elapsed().as_secs_f64()
-    ));
-
-    // ── 4c. AI reasoning ─────────────────────────────────────────
-    if cli.ai {
-        let eligible = findings
-            .iter()
-            .filter(|f| {
-                matches!(f.severity, Severity::Warning | Severity::Error) && f.suggestion.is_none()
-            })
-            .count();
-        let step = Step::new(format!("Running AI reasoning ({} findings)", eligible));
-        let ai_start = Instant::now();
-        let reasoner = AiReasoner::new(config.ai.clone(), cli.max_cost);
-        match reasoner.enrich(&mut findings, &repo_path) {
-            Ok(stats) => step.finish(&format!(
-                "{} enriched, {} false positives (${:.4}, {:.1}s)",
-                stats.findings_enriched,
-                stats.false_positives,
-                stats.cost_usd,
-                ai_start.elapsed().as_secs_f64()
-            )),
-            Err(e) => step.warn(e),
+    let mut graph_timings = Vec::new();
+    let mut complexity_delta = None;
+    let mut doc_coverage = None;
+    let active = shed.switch_off(&config);
+    let mut remaining = graph_phases(&active);
+    if build_graph && !remaining.is_empty() {
+        remaining.extend(scan_phases(&deferred_scans, true, &active, &settings));
+        stopped = memory_checkpoint(&mut memory, "graph analyzers", |step| {
+            shed.take(step, false, reads_base, &remaining, &scheduler)
+        });
+    }
+    let base = if build_graph && !stopped && !shed.drop_caches {
+        delta_base(
+            &repo_path,
+            diff_based,
+            delta_requested,
+            &settings,
+            &config,
+            &dispatcher,
+            &git_objects,
+        )
+    } else {
+        None
+    };
+    if build_graph && !stopped {
+        let active = shed.switch_off(&config);
+        let graph_config =
+            scheduler.admit_graph(&analyzer_dispatcher.graph_modules(&active), &active);
+        if let Some(ledger) = ledger.as_mut() {
+            analyzer_dispatcher.record_graph_coverage(&files, &graph_config, ledger);
+        }
+        let step = Step::new("Running graph analyzers");
+        let ga_start = Instant::now();
+        let (mut graph_findings, timings) = match base.as_ref().filter(|_| delta_requested) {
+            Some(base) => {
+                let (findings, timings, summary) =
+                    analyzer_dispatcher.run_graph_analyzers_delta(&graph, &graph_config, base);
+                complexity_delta = summary;
+                (findings, timings)
+            }
+            None => analyzer_dispatcher.run_graph_analyzers_timed(&graph, &graph_config),
+        };
+        record_timings(&mut scheduler, &analyzer_dispatcher, &timings);
+        if graph_config.modules.docs {
+            doc_coverage = Some(docs::coverage(&graph, &graph_config));
         }
+        // The files around the changed ones are context, not under review
+        if let Some(changed) = &changed_since {
+            graph_findings.retain(|f| changed.contains(&f.file));
+        }
+        let graph_count = graph_findings.len();
+        findings.extend(graph_findings);
+        graph_timings = timings;
+        step.finish(&format!(
+            "{} finding(s) ({:.1}s)",
+            graph_count,
+            ga_start.elapsed().as_secs_f64()
+        ));
     }
 
-    // ── 4d. Apply fixes ───────────────────────────────────────────
-    if cli.fix {
-        let step = Step::new("Applying fixes");
-        match apply_fixes(&findings) {
-            Ok(report) => step.finish(&format!(
-                "{} applied, {} suggestion-only",
-                report.applied, report.skipped
-            )),
-            Err(e) => step.warn(format!("failed: {}", e)),
+    // ── 4b''. Refactor verification ──────────────────────────────
+    if let Some(base) = base.as_ref().filter(|base| base.file_count() > 0) {
+        if config.modules.refactor
+            && !shed.skips("refactor")
+            && scheduler.admit(&Phase::new("refactor", Stage::GraphAnalyzers))
+        {
+            let step = Step::new("Checking call sites of changed signatures");
+            let ref_start = Instant::now();
+            let file_cache = FileGraphCache::at(&paths)
+                .with_remote(remote.clone())
+                .enabled(!cli.no_cache);
+            match repo_graph(&repo_path, &file_cache, &config, &dispatcher) {
+                Ok(head) => {
+                    let ref_findings = analyzer_dispatcher
+                        .run_refactor(&head, base, &config)
+                        .unwrap_or_default();
+                    step.finish(&format!(
+                        "{} finding(s) ({:.1}s)",
+                        ref_findings.len(),
+                        ref_start.elapsed().as_secs_f64()
+                    ));
+                    findings.extend(ref_findings);
+                }
+                Err(e) => step.warn(format!("{:#}", e)),
+            }
+            scheduler.record("refactor", ref_start.elapsed());
         }
     }
 
-    // ── 4d. Inline suppression ───────────────────────────────────
-    let mut all_suppressed: Vec<SuppressedFinding> = Vec::new();
-    let (new_findings, inline_suppressed) = filter_findings_by_inline(findings);
-    findings = new_findings;
-    all_suppressed.extend(inline_suppressed);
+    // ── 4b'''. Whole-repository scans ────────────────────────────
+    // Duplication (deferred to here under a budget), hotspots and the git
+    // history, admitted together
+    let active = shed.switch_off(&config);
+    let remaining = scan_phases(&deferred_scans, build_graph, &active, &settings);
+    if !stopped && !remaining.is_empty() {
+        stopped = memory_checkpoint(&mut memory, "whole-repository scans", |step| {
+            shed.take(step, false, false, &remaining, &scheduler)
+        });
+    }
+    let remaining: Vec<Phase> = remaining
+        .into_iter()
+        .filter(|phase| !stopped && !shed.skips(&phase.key))
+        .collect();
+    let scans: Vec<String> = scheduler
+        .admit_all(remaining, false)
+        .into_iter()
+        .map(|phase| phase.key)
+        .collect();
+    let admitted = |key: &str| scans.iter().any(|scan| scan == key);
 
-    // ── 4e. Per-path rule suppression ────────────────────────────
-    if !config.ignore.per_path.is_empty() {
-        let (new_findings, path_suppressed) =
-            filter_findings_by_path_rules(findings, &config.ignore.per_path, &repo_path);
-        findings = new_findings;
-        all_suppressed.extend(path_suppressed);
+    let deferred: Vec<&str> = deferred_scans
+        .into_iter()
+        .filter(|module| admitted(module))
+        .collect();
+    if !deferred.is_empty() {
+        let step = Step::new(format!("Running {} scan", deferred.join(", ")));
+        let scan_start = Instant::now();
+        let (scan_findings, timings) =
+            analyzer_dispatcher.run_modules_timed(&deferred, &analysis_files, &repo_path, &config);
+        step.finish(&format!(
+            "{} finding(s) ({:.1}s)",
+            scan_findings.len(),
+            scan_start.elapsed().as_secs_f64()
+        ));
+        record_timings(&mut scheduler, &analyzer_dispatcher, &timings);
+        findings.extend(scan_findings);
+        domain_timings.extend(timings);
     }
 
-    // ── 4f. Baseline suppression ───────────────────────────────────
-    if !cli.no_baseline {
-        if let Some(baseline) = Baseline::load(&repo_path)? {
-            let (new_findings, baseline_suppressed) =
-                filter_findings(findings, &baseline, &repo_path);
-            findings = new_findings;
-            all_suppressed.extend(baseline_suppressed);
+    // Hotspots (churn × complexity)
+    let mut hotspots = Vec::new();
+    if admitted("hotspots") {
+        let hot_start = Instant::now();
+        if let Some(result) = analyzer_dispatcher.run_hotspots(&graph, &repo_path, &config) {
+            let step = Step::new("Ranking hotspots");
+            match result {
+                Ok((hot_findings, report)) => {
+                    let shallow = if report.churn.shallow {
+                        ", shallow clone: partial history"
+                    } else {
+                        ""
+                    };
+                    step.finish(&format!(
+                        "{} hotspot(s) from {} commit(s) in {} days{}",
+                        hot_findings.len(),
+                        report.churn.commits,
+                        report.churn.window_days,
+                        shallow
+                    ));
+                    findings.extend(hot_findings);
+                    hotspots = report.ranking;
+                }
+                Err(e) => step.warn(e),
+            }
         }
+        scheduler.record("hotspots", hot_start.elapsed());
     }
 
-    // ── 5. Save Cache (CozoStore + metadata) ─────────────────────
-    let file_paths: Vec<PathBuf> = files
-        .iter()
-        .map(|f| f.strip_prefix(&repo_path).unwrap_or(f).to_path_buf())
-        .collect();
-
-    let checksums = GraphCache::build_file_checksums(&repo_path, &file_paths).unwrap_or_default();
-
-    let meta = GraphCacheMeta {
-        commit_hash: GraphCache::get_git_commit_hash(&repo_path),
-        timestamp: SystemTime::now(),
-        file_checksums: checksums,
-        revet_version: revet_core::VERSION.to_string(),
-    };
-
-    match create_store(&repo_path) {
-        Ok(store) => {
-            let _ = store.delete_snapshot("cached");
-            if let Err(e) = store.flush(&graph, "cached") {
-                eprintln!(
-                    "  {}: failed to save graph to store: {}",
-                    "warn".yellow(),
-                    e
-                );
+    // Ownership (git blame × active authors)
+    let mut orphans = Vec::new();
+    if admitted("ownership") {
+        let own_start = Instant::now();
+        if let Some(result) =
+            analyzer_dispatcher.run_ownership(&analysis_files, &graph, &repo_path, &config)
+        {
+            let step = Step::new("Checking ownership");
+            match result {
+                Ok((own_findings, report)) => {
+                    let mut notes = Vec::new();
+                    if report.skipped > 0 {
+                        notes.push(format!("{} file(s) over max_files", report.skipped));
+                    }
+                    if report.shallow {
+                        notes.push("shallow clone: partial history".to_string());
+                    }
+                    step.finish(&format!(
+                        "{} orphaned of {} file(s) blamed, {} active author(s){}",
+                        own_findings.len(),
+                        report.blamed,
+                        report.active,
+                        if notes.is_empty() {
+                            String::new()
+                        } else {
+                            format!(", {}", notes.join(", "))
+                        }
+                    ));
+                    findings.extend(own_findings);
+                    orphans = report.ranking;
+                }
+                Err(e) => step.warn(e),
             }
         }
-        Err(e) => {
-            eprintln!("  {}: failed to create store: {}", "warn".yellow(), e);
-        }
+        scheduler.record("ownership", own_start.elapsed());
     }
 
-    let cache = GraphCache::new(&repo_path);
-    if let Err(e) = cache.save(&graph, &meta) {
-        eprintln!("  {}: failed to save graph cache: {}", "warn".yellow(), e);
+    // Git history secrets
+    if admitted(schedule::HISTORY) {
+        let step = Step::new("Scanning git history for secrets");
+        let history_start = Instant::now();
+        let analyzer = SecretExposureAnalyzer::with_redaction(config.output.redact_secrets);
+        match analyzer.scan_history(&repo_path, HISTORY_SCAN_COMMITS, &git_objects) {
+            Ok(history) => {
+                let count = history.len();
+                for (i, mut finding) in history.into_iter().enumerate() {
+                    finding.id = format!("HIST-{:03}", i + 1);
+                    findings.push(finding);
+                }
+                step.finish(&format!(
+                    "{} finding(s) ({:.1}s)",
+                    count,
+                    history_start.elapsed().as_secs_f64()
+                ));
+            }
+            Err(e) => step.warn(e),
+        }
+        scheduler.record(schedule::HISTORY, history_start.elapsed());
     }
 
-    // ── 5b. Post GitHub PR comments ──────────────────────────────
-    if cli.post_comment {
-        post_github_comments(&findings, &repo_path, cli);
+    // Files an analyzer spent longer than `[performance]
+    // slow_file_warn_ms` on
+    let timings = domain_timings.iter().chain(&graph_timings);
+    if let Some(slow) = analyzer_dispatcher.run_slow_files(timings, &config) {
+        findings.extend(slow);
+    }
+
+    // External tools' findings, minus the ones revet reported as well
+    if !imported.is_empty() {
+        let step = Step::new("Merging imported findings");
+        let reviewed: HashSet<&PathBuf> = files.iter().collect();
+        let imported: Vec<Finding> = imported
+            .into_iter()
+            .filter(|f| !diff_based || reviewed.contains(&f.file))
+            .collect();
+        let count = imported.len();
+        let duplicates = import::merge(&mut findings, imported);
+        step.finish(&format!(
+            "{} finding(s), {} duplicate(s) dropped",
+            count - duplicates,
+            duplicates
+        ));
+    }
+
+    // Generated-file policy, vendored upstream lines, code kinds, then the
+    // profile confidence floor and `--only`
+    findings = generated.apply(findings, &repo_path);
+    let (kept, upstream) = vendored.apply(findings, &repo_path);
+    findings = kept;
+    if let Some(ledger) = ledger.as_mut() {
+        ledger.vendored_upstream(upstream);
+    }
+    findings = code_kinds.apply(findings, &repo_path);
+    let before = findings.len();
+    findings.retain(|f| settings.keeps(f));
+    let mut dropped = before - findings.len();
+    if let Some(project) = &project {
+        findings.retain(|f| project.contains(&repo_path, &f.file));
+    }
+
+    // Modules sampled out of their rollout only count what they would have
+    // reported: past inline and per-path suppression, before AI and fixes
+    let (kept, shadow) = analyzer_dispatcher.split_shadow(findings);
+    findings = kept;
+    let constructs = Constructs::from_graph(&graph);
+    let mut shadow = filter_inline_with(shadow, &constructs).kept;
+    if !config.ignore.per_path.is_empty() {
+        shadow = filter_findings_by_path_rules(shadow, &config.ignore.per_path, &repo_path).0;
+    }
+
+    // Code suggestions that don't parse in place are shown as prose only
+    let suggestions = suggestion::verify(&mut findings, &repo_path);
+
+    // ── 4c. AI reasoning ─────────────────────────────────────────
+    let mut ai_report = None;
+    if settings.ai && !stopped {
+        let mut ai_config = config.ai.clone();
+        ai_config.prioritization.only_new |= cli.ai_only_new;
+        if let Some(severity) = cli.ai_min_severity {
+            ai_config.prioritization.min_severity = severity.as_str().to_string();
+        }
+        let context = PriorityContext {
+            graph: build_graph.then_some(&graph),
+            baseline: baseline.as_ref(),
+            critical_paths: &config.risk.critical_paths,
+        };
+        let eligible = findings.iter().filter(|f| is_eligible(f)).count();
+        let reasoner = AiReasoner::new(ai_config, cli.max_cost);
+        if cli.estimate_only {
+            let step = Step::new(format!("Estimating AI cost ({} findings)", eligible));
+            match reasoner.estimate(&findings, &repo_path, &context) {
+                Ok(estimate) => {
+                    step.finish(&format!(
+                        "{} request(s), ~${:.4}{}",
+                        estimate.requests,
+                        estimate.estimated_cost,
+                        budget_note(estimate.skipped_budget)
+                    ));
+                    ai_report = Some(AiReport {
+                        estimate,
+                        actual_cost: None,
+                    });
+                }
+                Err(e) => step.warn(e),
+            }
+        } else {
+            let step = Step::new(format!("Running AI reasoning ({} findings)", eligible));
+            let ai_start = Instant::now();
+            match reasoner.enrich(&mut findings, &repo_path, &context) {
+                Ok(stats) => {
+                    step.finish(&format!(
+                        "{} enriched, {} false positives{} (${:.4}, {:.1}s)",
+                        stats.findings_enriched,
+                        stats.false_positives,
+                        budget_note(stats.skipped_budget),
+                        stats.cost_usd,
+                        ai_start.elapsed().as_secs_f64()
+                    ));
+                    ai_report = Some(AiReport {
+                        estimate: stats.estimate,
+                        actual_cost: Some(stats.cost_usd),
+                    });
+                }
+                Err(e) => step.warn(e),
+            }
+        }
+    }
+
+    // ── 4d. Inline suppression ───────────────────────────────────
+    let mut all_suppressed: Vec<SuppressedFinding> = Vec::new();
+    let mut inline = filter_inline_with(findings, &constructs);
+    findings = inline.kept;
+
+    // Audit the suppression comments themselves; unused ones are only
+    // meaningful when every file was analyzed by every analyzer
+    let (mut audit_findings, audit_suppressed) = analyzer_dispatcher.run_suppression_audit(
+        &analysis_files,
+        &inline.usage,
+        settings.scope == Scope::Full && !scheduler.skipped_any(),
+        &config,
+    );
+
+    // ── 4e. Apply fixes ───────────────────────────────────────────
+    // One pass over every finding, suppressed ones included, so edits
+    // that remove lines don't shift the others. Then everything that
+    // holds a line moves with the edits, the baseline's anchors too under
+    // `[baseline] auto_reconcile`
+    // Entries the fixes took out of the baseline, for the delta event
+    let mut reconciled: Option<Vec<BaselineEntry>> = None;
+    if cli.fix {
+        let step = Step::new("Applying fixes");
+        let fixable: Vec<Finding> = findings
+            .iter()
+            .chain(inline.suppressed.iter().map(|s| &s.finding))
+            .chain(&audit_findings)
+            .cloned()
+            .collect();
+        let mut journal = cli.journal(&repo_path, &paths);
+        match apply_fixes(&fixable, &mut journal) {
+            Ok(mut report) => {
+                step.finish(&format!(
+                    "{} applied, {} suggestion-only",
+                    report.applied, report.skipped
+                ));
+                findings = report.reconcile(findings);
+                audit_findings = report.reconcile(audit_findings);
+                inline.suppressed.retain_mut(|s| {
+                    let finding = &mut s.finding;
+                    let id = &finding.id;
+                    match report.remap(&finding.file, finding.line, || Anchor::Finding(id.clone()))
+                    {
+                        Some(line) => {
+                            finding.line = line;
+                            true
+                        }
+                        None => false,
+                    }
+                });
+                if let Some(baseline) = baseline.as_mut().filter(|_| config.baseline.auto_reconcile)
+                {
+                    let before = baseline.entries.clone();
+                    if baseline.reconcile(&mut report, &repo_path) {
+                        if let Err(e) = baseline.save_at(&paths, &mut journal) {
+                            eprintln!("  warn: reconciled baseline not saved: {:#}", e);
+                        }
+                        let kept: HashSet<String> =
+                            baseline.entries.iter().map(delta::entry_key).collect();
+                        let dropped: Vec<BaselineEntry> = before
+                            .into_iter()
+                            .filter(|e| !kept.contains(&delta::entry_key(e)))
+                            .collect();
+                        reconciled = Some(dropped).filter(|d| !d.is_empty());
+                    }
+                    print_dropped_entries(&report, &repo_path);
+                }
+            }
+            Err(e) => step.warn(format!("failed: {}", e)),
+        }
+        if let Err(e) = journal.finish() {
+            eprintln!("  warn: audit log not written: {:#}", e);
+        }
+    }
+    let (kept, floored): (Vec<Finding>, Vec<Finding>) = code_kinds
+        .apply(audit_findings, &repo_path)
+        .into_iter()
+        .partition(|f| settings.keeps(f));
+    dropped += floored.len();
+    findings.extend(kept);
+    all_suppressed.extend(inline.suppressed);
+    all_suppressed.extend(audit_suppressed);
+
+    // ── 4f. Per-path rule suppression ────────────────────────────
+    if !config.ignore.per_path.is_empty() {
+        let (new_findings, path_suppressed) =
+            filter_findings_by_path_rules(findings, &config.ignore.per_path, &repo_path);
+        findings = new_findings;
+        all_suppressed.extend(path_suppressed);
+    }
+
+    // ── 4g. Baseline suppression ───────────────────────────────────
+    if let Some(baseline) = &baseline {
+        let (new_findings, baseline_suppressed) = filter_findings(findings, baseline, &repo_path);
+        findings = new_findings;
+        all_suppressed.extend(baseline_suppressed);
+    }
+
+    // Configuration drift is about the change itself: the inline comments
+    // and baseline the change may edit don't apply to it
+    findings.extend(drift);
+
+    // ── 4h. Session ignores (`revet ignore`) ─────────────────────
+    let session_ignored = super::ignore::active(cli, &paths).map(|ignores| {
+        super::ignore::apply(&ignores, &mut findings, &mut all_suppressed, &repo_path)
+    });
+
+    // ── 4i. Policies ─────────────────────────────────────────────
+    let policies = policy::evaluate(&policies, &findings, &all_suppressed, &repo_path);
+
+    // ── 5. Save Cache (CozoStore + metadata) ─────────────────────
+    if build_graph {
+        save_graph(&repo_path, &paths, &files, &graph, &config);
+    }
+
+    // ── 6. Summary and run log ───────────────────────────────────
+    let mut summary = build_summary(&findings, &files, node_count);
+    summary.complexity_delta = complexity_delta;
+    summary.doc_coverage = doc_coverage;
+    summary.session_ignored = session_ignored;
+    summary.budget = scheduler.report();
+    summary.memory = memory.as_ref().map(MemoryGuard::report);
+    summary.shadow = analyzer_dispatcher.shadow_summary(&shadow);
+    summary.timings = cli.timings.map(|detail| {
+        let timings = domain_timings.iter().chain(&graph_timings);
+        let mut report = TimingReport::new(timings, &repo_path, detail == TimingsDetail::Full);
+        report.remote_cache = remote.as_ref().map(|r| r.stats());
+        report.memo = analyzer_dispatcher
+            .memo()
+            .zip(memo_before)
+            .map(|(memo, before)| memo.stats().since(&before));
+        report
+    });
+    let review_estimate = if diff_based {
+        DiffAnalyzer::new(&repo_path)
+            .map(|diff| diff.with_objects(git_objects.clone()))
+            .and_then(|diff| diff.changed_lines(&settings.diff_base, None))
+            .ok()
+            .map(|changed| {
+                ReviewEstimate::collect(
+                    &repo_path,
+                    &paths,
+                    &config,
+                    &graph,
+                    &changed,
+                    &summary,
+                    risk.as_ref(),
+                    &git_objects,
+                )
+            })
+    } else {
+        None
+    };
+    // Last, once everything that reads git objects has run
+    summary.degraded = git_objects.degradations();
+    if let Some(out) = progressive.as_mut() {
+        for finding in &findings {
+            out.report_finding(finding, &repo_path);
+        }
+        out.end_run(&RunEnd::new(&summary, &[]));
     }
 
-    // ── 6. Output ────────────────────────────────────────────────
-    let summary = build_summary(&findings, &files, node_count);
+    // A full run's findings tell which baseline entries still match
+    let branch = DiffAnalyzer::new(&repo_path)
+        .ok()
+        .and_then(|analyzer| analyzer.current_branch());
+    let full = !diff_based && project.is_none();
+    let owners = CodeOwners::load(&repo_path);
+    let baseline_record = baseline
+        .as_ref()
+        .filter(|_| full && !scheduler.skipped_any())
+        .map(|baseline| {
+            let seen: Vec<Finding> = findings
+                .iter()
+                .chain(all_suppressed.iter().map(|s| &s.finding))
+                .cloned()
+                .collect();
+            let snapshot =
+                Snapshot::of(baseline, &seen, &repo_path, &revet_core::baseline::today());
+            BaselineRecord::since_last(&paths, branch.as_deref(), snapshot, &owners)
+        });
+    // Without a record, only the entries the fixes dropped count
+    let fix_event = reconciled.map(|dropped| match &baseline_record {
+        Some(record) => (record.since.clone(), record.delta.clone()),
+        None => (
+            branch
+                .as_deref()
+                .and_then(|branch| run_log::last_baseline(&paths, branch))
+                .map(|(since, _)| since),
+            Delta::dropped(&dropped, &owners),
+        ),
+    });
 
     // Write run log (best-effort — don't fail the review on log errors)
     let run_id = run_log::new_run_id();
     let run_log_saved = run_log::save_run_log(
-        &repo_path,
+        &paths,
         &run_id,
         start.elapsed().as_secs_f64(),
         &findings,
         &all_suppressed,
         &summary,
+        &hotspots,
+        &orphans,
+        ledger
+            .as_ref()
+            .zip(cli.coverage_report)
+            .map(|(ledger, detail)| {
+                let mut report = ledger.report(&repo_path, detail == CoverageDetail::Full);
+                report.degraded = summary.degraded.clone();
+                report
+            }),
+        &RunScope {
+            branch: branch.clone(),
+            full,
+        },
+        baseline_record,
         &repo_path,
     )
     .is_ok();
+    if let Some((since, delta)) = fix_event {
+        let event = DeltaEvent::new(&repo_path, branch, "review --fix", since, &delta);
+        webhook::notify(&config.reporting, &event, cli.webhook_dry_run);
+    }
+    // What `revet explain <id>` reads (best-effort as well)
+    let _ = run_log::save_last_run(&paths, &run_id, &findings, &all_suppressed, &repo_path);
+    // Estimates for the next --time-budget run (best-effort as well)
+    let _ = scheduler.timings().save(&paths);
+
+    Ok(ReviewRun {
+        repo_path,
+        config,
+        settings,
+        findings,
+        suppressed: all_suppressed,
+        dropped,
+        summary,
+        policies,
+        blast_radius,
+        risk,
+        review_estimate,
+        ledger,
+        ai: ai_report,
+        run_id: run_log_saved.then_some(run_id),
+        domain_timings,
+        graph_timings,
+        suggestions,
+        rollout: analyzer_dispatcher.rollout().clone(),
+        elapsed: start.elapsed(),
+    })
+}
 
-    let mut out = make_formatter(format, &repo_path, cli.show_suppressed);
-    if let Some(ref br) = blast_radius {
-        out.write_blast_radius(br);
+// ── Helpers ──────────────────────────────────────────────────────
+
+/// Record analyzer timings with the scheduler, summed per `[modules]` entry
+fn record_timings(
+    scheduler: &mut Scheduler,
+    dispatcher: &AnalyzerDispatcher,
+    timings: &[AnalyzerTiming],
+) {
+    for timing in timings {
+        if let Some(module) = dispatcher.module_of(&timing.prefix) {
+            scheduler.record(module, timing.duration);
+            if let Some(bytes) = timing.peak_alloc {
+                scheduler.record_peak(module, bytes);
+            }
+        }
     }
-    for f in &findings {
-        out.write_finding(f, &repo_path);
+}
+
+/// Whole-repository scans the review runs after the graph analyzers: the
+/// `deferred` content modules, hotspots (which need the `graph`),
+/// ownership and the git history
+fn scan_phases(
+    deferred: &[&str],
+    graph: bool,
+    config: &RevetConfig,
+    settings: &ProfileSettings,
+) -> Vec<Phase> {
+    let mut phases: Vec<Phase> = deferred
+        .iter()
+        .map(|module| Phase::new(*module, Stage::Scans))
+        .collect();
+    if graph && config.modules.hotspots {
+        phases.push(Phase::new("hotspots", Stage::Scans));
     }
-    if cli.show_suppressed {
-        for sf in &all_suppressed {
-            out.write_suppressed(sf, &repo_path);
+    if config.modules.ownership {
+        phases.push(Phase::new("ownership", Stage::Scans));
+    }
+    if settings.history_secrets && config.modules.security {
+        phases.push(Phase::new(schedule::HISTORY, Stage::Scans));
+    }
+    phases
+}
+
+/// Load a review shed to stay under `[performance] max_memory_mb`
+#[derive(Debug, Default)]
+struct Shed {
+    /// Per-file content analyzers run one directory partition at a time
+    small_batches: bool,
+    /// The base-tree contents of the diff are not read
+    drop_caches: bool,
+    /// Keys of the phases skipped
+    skipped: Vec<String>,
+}
+
+impl Shed {
+    /// Take `step` before a stage with `remaining` phases still to run.
+    /// `batches` and `caches` say whether there are analyzer batches left
+    /// to shrink and caches left to drop. Returns what the step did, or
+    /// `None` when it has nothing to act on.
+    fn take(
+        &mut self,
+        step: Shedding,
+        batches: bool,
+        caches: bool,
+        remaining: &[Phase],
+        scheduler: &Scheduler,
+    ) -> Option<String> {
+        match step {
+            Shedding::SmallerBatches => batches.then(|| {
+                self.small_batches = true;
+                "content analyzers run one directory partition at a time".to_string()
+            }),
+            Shedding::DropCaches => caches.then(|| {
+                self.drop_caches = true;
+                "diff base contents not read: complexity in absolute mode, no refactor verification"
+                    .to_string()
+            }),
+            // Graph analyzers only run on the in-memory graph
+            Shedding::LazyGraph => None,
+            // The largest recorded peak allocation first, then the longest
+            // expected run
+            Shedding::SkipAnalyzers => {
+                let phase = remaining
+                    .iter()
+                    .filter(|phase| !self.skips(&phase.key))
+                    .max_by_key(|phase| (scheduler.peak(&phase.key), scheduler.estimate(phase)))?;
+                self.skipped.push(phase.key.clone());
+                Some(phase.label())
+            }
         }
     }
-    out.write_summary(
-        &summary,
-        &all_suppressed,
-        start.elapsed(),
-        if run_log_saved { Some(&run_id) } else { None },
-    );
-    out.finalize();
 
-    // ── 7. Timings (optional) ────────────────────────────────────
-    if cli.timings {
-        print_timings(&domain_timings, &graph_timings);
+    fn skips(&self, key: &str) -> bool {
+        self.skipped.iter().any(|skipped| skipped == key)
     }
 
-    // Quality gate (--gate) takes precedence over --fail-on
-    let gate = cli
-        .gate
-        .as_deref()
-        .map(GateConfig::from_flag)
-        .unwrap_or_else(|| config.gate.clone());
+    /// `config` with the skipped `[modules]` entries switched off
+    fn switch_off(&self, config: &RevetConfig) -> RevetConfig {
+        let mut config = config.clone();
+        for key in &self.skipped {
+            config.modules.set(key, false);
+        }
+        config
+    }
+}
 
-    let exceeded = if !gate.is_empty() {
-        summary.exceeds_gate(&gate)
-    } else {
-        let fail_on = cli.fail_on.as_deref().unwrap_or(&config.general.fail_on);
-        summary.exceeds_threshold(fail_on)
+/// Shed load before `stage` while memory is past the high-water mark,
+/// logging each step. Returns whether the review must stop.
+fn memory_checkpoint(
+    memory: &mut Option<MemoryGuard>,
+    stage: &str,
+    shed: impl FnMut(Shedding) -> Option<String>,
+) -> bool {
+    let Some(memory) = memory.as_mut() else {
+        return false;
     };
+    let taken = memory.shed().len();
+    let stop = memory.checkpoint(stage, shed);
+    for step in &memory.shed()[taken..] {
+        eprintln!("  {}: memory: {}", "warn".yellow(), step);
+    }
+    if stop {
+        eprintln!(
+            "  {}: memory still over [performance] max_memory_mb before {}; stopping with the findings so far",
+            "error".red(),
+            stage
+        );
+    }
+    stop
+}
 
-    if exceeded {
-        Ok(ReviewExitCode::FindingsExceedThreshold)
+/// `", N skipped for budget"` for the AI step line, empty when none were
+fn budget_note(skipped: usize) -> String {
+    if skipped == 0 {
+        String::new()
     } else {
-        Ok(ReviewExitCode::Success)
+        format!(", {} skipped for budget", skipped)
     }
 }
 
-// ── Helpers ──────────────────────────────────────────────────────
+/// Compile `[[policy]]`; with `--explain-policy`, the name must be one of
+/// them
+fn compile_policies(config: &RevetConfig, cli: &crate::Cli) -> Result<Vec<Policy>> {
+    let policies = policy::compile(&config.policies)
+        .map_err(|errors| anyhow::anyhow!("invalid [[policy]]:\n  {}", errors.join("\n  ")))?;
+    if let Some(name) = &cli.explain_policy {
+        if !policies.iter().any(|p| &p.name == name) {
+            if policies.is_empty() {
+                anyhow::bail!("--explain-policy {:?}: no [[policy]] is configured", name);
+            }
+            let names: Vec<&str> = policies.iter().map(|p| p.name.as_str()).collect();
+            anyhow::bail!(
+                "--explain-policy {:?}: unknown policy. Configured: {}",
+                name,
+                names.join(", ")
+            );
+        }
+    }
+    Ok(policies)
+}
+
+/// `--explain-policy`: the policy, its outcome and every finding it
+/// matched, with the status the filter saw
+fn explain_policy(review: &ReviewRun, name: &str, locale: Locale) {
+    let Some(result) = review.policies.get(name) else {
+        return;
+    };
+    let outcome = match (result.passed, result.action) {
+        (true, _) => "passed".green().bold(),
+        (false, Action::Fail) => "failed".red().bold(),
+        (false, Action::Warn) => "violated (warn)".yellow().bold(),
+        (false, Action::RequireEscalation) => "violated (requires escalation)".yellow().bold(),
+    };
+    println!("  {} {}  {}", "Policy".bold(), result.name.bold(), outcome);
+    if let Some(description) = &result.description {
+        println!("  {}", description);
+    }
+    println!("  {:<16} {}", "Filter:".dimmed(), result.filter);
+    println!(
+        "  {:<16} {} (action = {})",
+        "Violated when:".dimmed(),
+        result.condition,
+        result.action.as_str()
+    );
+    println!("  {:<16} {}", "Matching:".dimmed(), result.count);
+    if result.matches.is_empty() {
+        return;
+    }
+    println!();
+    for m in &result.matches {
+        let path = m
+            .finding
+            .file
+            .strip_prefix(&review.repo_path)
+            .unwrap_or(&m.finding.file);
+        let status = match m.status {
+            Status::New => String::new(),
+            other => format!("  [{}]", other.as_str()),
+        };
+        println!(
+            "  {:<10} {:<8} {}:{}{}",
+            m.finding.id,
+            m.finding.severity.to_string(),
+            path.display(),
+            m.finding.line,
+            status.dimmed()
+        );
+        println!(
+            "  {:<10} {}",
+            "",
+            m.finding.localized_message(locale).dimmed()
+        );
+    }
+}
+
+/// Report the baseline entries `--fix` dropped because a fix deleted the
+/// line they were anchored to
+pub(crate) fn print_dropped_entries(report: &FixReport, repo_path: &Path) {
+    for dropped in &report.dropped {
+        if let Anchor::BaselineEntry(message) = &dropped.anchor {
+            let file = dropped
+                .file
+                .strip_prefix(repo_path)
+                .unwrap_or(&dropped.file);
+            eprintln!(
+                "  {}: baseline entry for {}:{} dropped, a fix deleted its line: {}",
+                "reconciled".cyan(),
+                file.display(),
+                dropped.line,
+                message
+            );
+        }
+    }
+}
+
+/// Error for `--progressive github` with an annotating `--format`
+pub(crate) const PROGRESSIVE_GITHUB_TWICE: &str =
+    "--progressive github already annotates every finding; use it with --format terminal, json or sarif";
+
+/// The `[[vendored]]` files among `files` (under `root`, the repository
+/// or a checkout of it), compared with their upstream; warns about upstream
+/// refs that don't resolve
+pub(crate) fn classify_vendored(
+    config: &RevetConfig,
+    repo_path: &Path,
+    root: &Path,
+    files: &[PathBuf],
+) -> VendoredSet {
+    let vendored = VendoredFiles::resolve(config, repo_path);
+    for overlay in vendored.overlays() {
+        if let Some(error) = &overlay.error {
+            eprintln!(
+                "  {}: [[vendored]] {}: {}; using fallback = \"{}\"",
+                "warn".yellow(),
+                overlay.path.display(),
+                error,
+                overlay.fallback.as_str()
+            );
+        }
+    }
+    vendored.classify(files, root)
+}
+
+/// What the review keeps of a partition's findings: the generated-file
+/// policy, vendored upstream lines, code kinds, the confidence floor, inline
+/// suppression, per-path rules and the baseline, as applied to all findings
+/// once the analyzers are done
+#[allow(clippy::too_many_arguments)]
+fn settled(
+    findings: Vec<Finding>,
+    generated: &GeneratedSet,
+    vendored: &VendoredSet,
+    code_kinds: &CodeKinds,
+    settings: &ProfileSettings,
+    config: &RevetConfig,
+    baseline: Option<&Baseline>,
+    repo_path: &Path,
+) -> Vec<Finding> {
+    let findings = generated.apply(findings, repo_path);
+    let findings = vendored.apply(findings, repo_path).0;
+    let mut findings = code_kinds.apply(findings, repo_path);
+    findings.retain(|f| settings.keeps(f));
+    suggestion::verify(&mut findings, repo_path);
+    let mut findings = filter_inline(findings).kept;
+    if !config.ignore.per_path.is_empty() {
+        findings = filter_findings_by_path_rules(findings, &config.ignore.per_path, repo_path).0;
+    }
+    if let Some(baseline) = baseline {
+        findings = filter_findings(findings, baseline, repo_path).0;
+    }
+    findings
+}
+
+/// Persist the graph to the CozoStore snapshot and the graph cache
+fn save_graph(
+    repo_path: &Path,
+    paths: &StoragePaths,
+    files: &[PathBuf],
+    graph: &CodeGraph,
+    config: &RevetConfig,
+) {
+    let file_paths: Vec<PathBuf> = files
+        .iter()
+        .map(|f| f.strip_prefix(repo_path).unwrap_or(f).to_path_buf())
+        .collect();
+
+    let checksums = GraphCache::build_file_checksums(repo_path, &file_paths).unwrap_or_default();
+
+    let meta = GraphCacheMeta {
+        commit_hash: GraphCache::get_git_commit_hash(repo_path),
+        timestamp: SystemTime::now(),
+        file_checksums: checksums,
+        revet_version: revet_core::VERSION.to_string(),
+    };
+
+    match create_store_at(paths) {
+        Ok(store) => {
+            let store = store.with_full_every(config.cache.full_snapshot_every);
+            let _ = store.delete_snapshot("cached");
+            if let Err(e) = store.flush(graph, "cached") {
+                eprintln!(
+                    "  {}: failed to save graph to store: {}",
+                    "warn".yellow(),
+                    e
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("  {}: failed to create store: {}", "warn".yellow(), e);
+        }
+    }
+
+    let cache = GraphCache::at(paths, CacheFormat::from_config(&config.cache));
+    if let Err(e) = cache.save(graph, &meta) {
+        eprintln!("  {}: failed to save graph cache: {}", "warn".yellow(), e);
+    }
+}
 
 /// Load the old (baseline) graph for impact analysis.
 ///
-/// Tries: msgpack cache → CozoStore → git blobs → None.
+/// Tries: graph cache (msgpack or compact) → CozoStore → git blobs → None.
 /// A single spinner covers all attempts; its message is updated between tries.
 fn load_old_graph(
     repo_path: &Path,
-    cli: &crate::Cli,
+    paths: &StoragePaths,
+    settings: &ProfileSettings,
     config: &RevetConfig,
     dispatcher: &ParserDispatcher,
+    objects: &GitObjects,
 ) -> Option<CodeGraph> {
     let step = Step::new("Loading baseline graph");
     let baseline_start = Instant::now();
 
-    // 1. Try msgpack cache (fast path — serialized whole graph)
-    let cache = GraphCache::new(repo_path);
+    // 1. Try the graph cache (fast path — serialized whole graph)
+    let cache = GraphCache::at(paths, CacheFormat::from_config(&config.cache));
     match cache.load() {
         Ok(Some((cached_graph, _))) => {
             step.finish(&format!(
@@ -613,7 +2134,7 @@ fn load_old_graph(
     }
 
     // 2. Try CozoStore (slower fallback)
-    if let Ok(store) = create_store(repo_path) {
+    if let Ok(Some(store)) = open_store(paths) {
         let snaps = store.snapshots().unwrap_or_default();
         if snaps.iter().any(|s| s.name == "cached") {
             step.update("Loading baseline graph from store...");
@@ -632,9 +2153,10 @@ fn load_old_graph(
     }
 
     // 3. Try building from git blobs at the base ref
-    let base = cli.diff.as_deref().unwrap_or(&config.general.diff_base);
+    let base = settings.diff_base.as_str();
     match GitTreeReader::new(repo_path) {
         Ok(reader) => {
+            let reader = reader.with_objects(objects.clone());
             step.update(format!("Building baseline graph from git ({})...", base));
             match reader.build_graph_at_ref(base, repo_path, dispatcher) {
                 Ok(blob_graph) => {
@@ -662,76 +2184,246 @@ fn load_old_graph(
     }
 }
 
+/// The base side of the diff, read when the reviewed files come from a diff
+/// and either complexity delta mode (`--complexity-delta` or
+/// `[analyzers.complexity] mode = "delta"`) or refactor verification needs
+/// it. `None` means absolute mode and no refactor checks.
+fn delta_base(
+    repo_path: &Path,
+    diff_based: bool,
+    delta: bool,
+    settings: &ProfileSettings,
+    config: &RevetConfig,
+    dispatcher: &ParserDispatcher,
+    objects: &GitObjects,
+) -> Option<DeltaBase> {
+    if !(delta || config.modules.refactor) || !diff_based {
+        return None;
+    }
+    let base = settings.diff_base.as_str();
+    let step = Step::new(format!("Reading diff baseline ({})", base));
+    match DeltaBase::at_ref(repo_path, base, dispatcher, objects) {
+        Ok(delta) => {
+            step.finish(&format!("{} modified file(s)", delta.file_count()));
+            Some(delta)
+        }
+        Err(e) => {
+            if delta {
+                step.warn(format!("{:#} — using absolute thresholds", e));
+            } else {
+                step.warn(format!("{:#}", e));
+            }
+            None
+        }
+    }
+}
+
+/// The code graph of every parseable file in the repository. Refactor
+/// verification needs it in diff mode, where the review graph only covers
+/// the changed files and so misses the callers the diff forgot.
+fn repo_graph(
+    repo_path: &Path,
+    file_cache: &FileGraphCache,
+    config: &RevetConfig,
+    dispatcher: &ParserDispatcher,
+) -> Result<CodeGraph> {
+    let files = discover_files(
+        repo_path,
+        &dispatcher.supported_extensions(),
+        &config.ignore.paths,
+    )?;
+    let (graph, _, _, _) =
+        dispatcher.parse_files_incremental(&files, repo_path.to_path_buf(), file_cache);
+    file_cache.evict_unused();
+    Ok(graph)
+}
+
+/// Files to review, and whether they are the files changed since the diff
+/// base (rather than a full scan)
+#[allow(clippy::too_many_arguments)]
 fn discover_review_files(
     repo_path: &Path,
-    cli: &crate::Cli,
+    settings: &ProfileSettings,
     config: &RevetConfig,
     all_extensions: &[&str],
     extra_filenames: &[&str],
-) -> Result<Vec<PathBuf>> {
-    if cli.full {
-        return full_scan(repo_path, all_extensions, extra_filenames, config);
+    extra_dotfiles: &[&str],
+    objects: &GitObjects,
+    mut ledger: Option<&mut CoverageLedger>,
+) -> Result<(Vec<PathBuf>, bool)> {
+    let full_scan = |ledger| {
+        full_scan(
+            repo_path,
+            all_extensions,
+            extra_filenames,
+            extra_dotfiles,
+            config,
+            ledger,
+        )
+        .map(|files| (files, false))
+    };
+    if settings.scope == Scope::Full {
+        return full_scan(ledger);
     }
 
-    // Try diff-based discovery
-    let base = cli.diff.as_deref().unwrap_or(&config.general.diff_base);
+    // Try diff-based discovery; `changed` scope never widens to a full scan
+    let base = settings.diff_base.as_str();
+    let changed_only = settings.scope == Scope::Changed;
 
-    match DiffAnalyzer::new(repo_path) {
+    match DiffAnalyzer::new(repo_path).map(|analyzer| analyzer.with_objects(objects.clone())) {
         Ok(analyzer) => {
             let step = Step::new(format!("Discovering changed files (diff vs {})", base));
             match analyzer.get_diff(base, None) {
                 Ok(diff) => {
                     let changed = analyzer.get_changed_files(&diff)?;
-                    let files: Vec<PathBuf> = changed
-                        .into_iter()
-                        .filter_map(|cf| {
-                            let abs = repo_path.join(&cf.path);
-                            if abs.exists()
-                                && (has_extension(&cf.path, all_extensions)
-                                    || has_filename(&cf.path, extra_filenames))
-                            {
-                                Some(abs)
-                            } else {
-                                None
+                    let mut files = Vec::new();
+                    for cf in changed {
+                        let abs = repo_path.join(&cf.path);
+                        if !abs.exists() {
+                            continue;
+                        }
+                        if is_analysis_input(
+                            &cf.path,
+                            all_extensions,
+                            extra_filenames,
+                            extra_dotfiles,
+                        ) {
+                            if let Some(ledger) = ledger.as_deref_mut() {
+                                ledger.discovered(&abs);
                             }
-                        })
-                        .collect();
+                            files.push(abs);
+                        } else if let Some(ledger) = ledger.as_deref_mut() {
+                            ledger.dropped(&abs, SkipReason::UnsupportedExtension);
+                        }
+                    }
 
+                    if files.is_empty() && changed_only {
+                        step.skip("No supported changed files");
+                        return Ok((files, true));
+                    }
                     if files.is_empty() {
                         step.skip("No supported changed files — falling back to full scan");
-                        return full_scan(repo_path, all_extensions, extra_filenames, config);
+                        return full_scan(ledger);
                     }
 
                     step.finish(&format!("{} files", files.len()));
-                    Ok(files)
+                    Ok((files, true))
+                }
+                Err(_) if changed_only => {
+                    step.skip(&format!(
+                        "Could not diff against '{}' — nothing to scan",
+                        base
+                    ));
+                    Ok((Vec::new(), false))
                 }
                 Err(_) => {
                     step.skip(&format!(
                         "Could not diff against '{}' — falling back to full scan",
                         base
                     ));
-                    full_scan(repo_path, all_extensions, extra_filenames, config)
+                    full_scan(ledger)
                 }
             }
         }
+        Err(_) if changed_only => {
+            eprintln!("  {}", "Not a git repository — nothing to scan".dimmed());
+            Ok((Vec::new(), false))
+        }
         Err(_) => {
             eprintln!("  {}", "Not a git repository — running full scan".dimmed());
-            full_scan(repo_path, all_extensions, extra_filenames, config)
+            full_scan(ledger)
+        }
+    }
+}
+
+/// `--changed-since <rev>`: the files changed since `rev` that discovery
+/// finds (so `.gitignore` and `[ignore] paths` still apply), and all the
+/// files it finds. Changes that discovery skips are recorded in `ledger`.
+#[allow(clippy::too_many_arguments)]
+fn changed_since_files(
+    repo_path: &Path,
+    rev: &str,
+    config: &RevetConfig,
+    all_extensions: &[&str],
+    extra_filenames: &[&str],
+    extra_dotfiles: &[&str],
+    objects: &GitObjects,
+    ledger: Option<&mut CoverageLedger>,
+) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+    let step = Step::new(format!("Discovering files changed since {}", rev));
+    let changed = DiffAnalyzer::new(repo_path)
+        .map(|analyzer| analyzer.with_objects(objects.clone()))
+        .and_then(|analyzer| {
+            let diff = analyzer.get_diff(rev, None)?;
+            analyzer.get_changed_files(&diff)
+        })
+        .map_err(|e| {
+            exit::usage(format!(
+                "--changed-since: can't diff against '{}': {:#}",
+                rev, e
+            ))
+        })?;
+    let allowed: BTreeSet<PathBuf> = changed
+        .iter()
+        .map(|cf| repo_path.join(&cf.path))
+        .filter(|path| path.exists())
+        .collect();
+
+    let discovered = discover_files_with_dotfiles(
+        repo_path,
+        all_extensions,
+        extra_filenames,
+        extra_dotfiles,
+        &config.ignore.paths,
+    )?;
+    let files: Vec<PathBuf> = discovered
+        .iter()
+        .filter(|f| allowed.contains(*f))
+        .cloned()
+        .collect();
+    if let Some(ledger) = ledger {
+        for path in &allowed {
+            if files.contains(path) {
+                ledger.discovered(path);
+            } else if is_analysis_input(path, all_extensions, extra_filenames, extra_dotfiles) {
+                ledger.dropped(path, SkipReason::ExcludedByConfig);
+            } else {
+                ledger.dropped(path, SkipReason::UnsupportedExtension);
+            }
         }
     }
+    step.finish(&format!("{} of {} files", files.len(), discovered.len()));
+    Ok((files, discovered))
 }
 
 fn full_scan(
     repo_path: &Path,
     extensions: &[&str],
     filenames: &[&str],
+    dotfiles: &[&str],
     config: &RevetConfig,
+    ledger: Option<&mut CoverageLedger>,
 ) -> Result<Vec<PathBuf>> {
     let step = Step::new("Discovering files (full scan)");
-    let files = if filenames.is_empty() {
+    let files = if let Some(ledger) = ledger {
+        discover_files_recorded(
+            repo_path,
+            extensions,
+            filenames,
+            dotfiles,
+            &config.ignore.paths,
+            ledger,
+        )?
+    } else if filenames.is_empty() && dotfiles.is_empty() {
         discover_files(repo_path, extensions, &config.ignore.paths)?
     } else {
-        discover_files_extended(repo_path, extensions, filenames, &config.ignore.paths)?
+        discover_files_with_dotfiles(
+            repo_path,
+            extensions,
+            filenames,
+            dotfiles,
+            &config.ignore.paths,
+        )?
     };
     step.finish(&format!("{} files", files.len()));
     Ok(files)
@@ -746,6 +2438,21 @@ pub(crate) fn has_extension(path: &Path, extensions: &[&str]) -> bool {
     extensions.contains(&with_dot.as_str())
 }
 
+/// Whether a changed or watched file is input for the review: a dotfile
+/// matching `dotfiles`, or another file with one of `extensions` or
+/// `filenames`
+pub(crate) fn is_analysis_input(
+    path: &Path,
+    extensions: &[&str],
+    filenames: &[&str],
+    dotfiles: &[&str],
+) -> bool {
+    if is_dotfile(path) {
+        return matches_dotfile(path, dotfiles);
+    }
+    has_extension(path, extensions) || has_filename(path, filenames)
+}
+
 pub(crate) fn has_filename(path: &Path, filenames: &[&str]) -> bool {
     if filenames.is_empty() {
         return false;
@@ -767,6 +2474,10 @@ pub(crate) fn build_summary(
         ..Default::default()
     };
     for f in findings {
+        if f.generated {
+            summary.generated += 1;
+            continue;
+        }
         match f.severity {
             Severity::Error => summary.errors += 1,
             Severity::Warning => summary.warnings += 1,
@@ -780,7 +2491,7 @@ pub(crate) fn build_summary(
     summary
 }
 
-fn ext_to_language(path: &Path) -> String {
+pub(crate) fn ext_to_language(path: &Path) -> String {
     match path.extension().and_then(|e| e.to_str()) {
         Some("rs") => "Rust",
         Some("py") => "Python",
@@ -809,11 +2520,18 @@ fn ext_to_language(path: &Path) -> String {
     .to_string()
 }
 
-/// Post findings as inline GitHub PR review comments.
+/// Post findings as inline GitHub PR review comments, plus the risk summary
+/// comment when impact analysis ran.
 ///
 /// Filters to diff-only findings, deduplicates against existing comments,
 /// and logs a summary. Exits gracefully if GitHub context is not available.
-fn post_github_comments(findings: &[Finding], repo_path: &Path, cli: &crate::Cli) {
+fn post_github_comments(
+    findings: &[Finding],
+    risk: Option<&RiskReport>,
+    repo_path: &Path,
+    base: &str,
+    locale: Locale,
+) {
     let ctx = match github_comment::GitHubContext::from_env() {
         Some(c) => c,
         None => {
@@ -829,7 +2547,6 @@ fn post_github_comments(findings: &[Finding], repo_path: &Path, cli: &crate::Cli
     // Filter to only findings on changed lines
     let diff_findings = match DiffAnalyzer::new(repo_path) {
         Ok(analyzer) => {
-            let base = cli.diff.as_deref().unwrap_or("main");
             match analyzer.get_all_changed_lines(base) {
                 Ok(diff_map) => {
                     let (kept, _) =
@@ -848,7 +2565,7 @@ fn post_github_comments(findings: &[Finding], repo_path: &Path, cli: &crate::Cli
         ctx.pr_number
     ));
 
-    match github_comment::post_review_comments(&diff_findings, repo_path, &ctx) {
+    match github_comment::post_review_comments(&diff_findings, repo_path, &ctx, locale) {
         Ok((posted, off_diff, dupes)) => {
             step.finish(&format!(
                 "{} posted, {} duplicate(s) skipped, {} off-diff skipped",
@@ -859,9 +2576,90 @@ fn post_github_comments(findings: &[Finding], repo_path: &Path, cli: &crate::Cli
             step.warn(format!("failed: {}", e));
         }
     }
+
+    if let Some(risk) = risk.filter(|r| !r.symbols.is_empty()) {
+        let step = Step::new(format!(
+            "Posting risk summary to GitHub PR #{}",
+            ctx.pr_number
+        ));
+        match github_comment::post_risk_summary(risk, &ctx) {
+            Ok(true) => step.finish("updated"),
+            Ok(false) => step.finish("posted"),
+            Err(e) => step.warn(format!("failed: {}", e)),
+        }
+    }
 }
 
 /// Print a per-analyzer timing breakdown table to stderr.
+/// Findings and code suggestion checks per rule, then the findings of
+/// modules in shadow mode, to stderr
+fn print_rule_stats(findings: &[Finding], suggestions: &SuggestionStats, shadow: &[ShadowModule]) {
+    let mut rules: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+    for finding in findings {
+        *rules.entry(suggestion::rule_of(finding)).or_default() += 1;
+    }
+    for rule in suggestions.checked.keys() {
+        rules.entry(rule.clone()).or_default();
+    }
+    if rules.is_empty() {
+        print_shadow_stats(shadow);
+        return;
+    }
+
+    eprintln!();
+    eprintln!("  {}", "Rule stats".bold());
+    eprintln!(
+        "  {:<36} {:>8}  {:>5}  {:>7}",
+        "Rule".dimmed(),
+        "Findings".dimmed(),
+        "Code".dimmed(),
+        "Demoted".dimmed()
+    );
+    eprintln!("  {}", "─".repeat(60).dimmed());
+    for (rule, count) in &rules {
+        let checked = suggestions.checked.get(rule).copied().unwrap_or(0);
+        let demoted = suggestions.demoted.get(rule).copied().unwrap_or(0);
+        eprintln!(
+            "  {:<36} {:>8}  {:>5}  {:>7}",
+            rule, count, checked, demoted
+        );
+    }
+    let demoted = suggestions.demoted_total();
+    if demoted > 0 {
+        eprintln!(
+            "  {}",
+            format!(
+                "{} code suggestion(s) did not parse and were shown as prose",
+                demoted
+            )
+            .yellow()
+        );
+    }
+    print_shadow_stats(shadow);
+}
+
+/// Findings per rule of the modules that ran in shadow mode
+fn print_shadow_stats(shadow: &[ShadowModule]) {
+    if shadow.is_empty() {
+        return;
+    }
+    eprintln!();
+    eprintln!("  {}", "Shadow rollout (not reported)".bold());
+    eprintln!("  {:<36} {:>8}", "Rule".dimmed(), "Findings".dimmed());
+    eprintln!("  {}", "─".repeat(60).dimmed());
+    for module in shadow {
+        eprintln!(
+            "  {:<36} {:>8}  {}",
+            module.module,
+            module.total(),
+            format!("rollout {}%", module.percent).dimmed()
+        );
+        for (rule, count) in &module.rules {
+            eprintln!("    {:<34} {:>8}", rule, count);
+        }
+    }
+}
+
 fn print_timings(domain: &[AnalyzerTiming], graph: &[AnalyzerTiming]) {
     let all: Vec<&AnalyzerTiming> = domain.iter().chain(graph.iter()).collect();
     if all.is_empty() {
@@ -914,6 +2712,75 @@ fn print_timings(domain: &[AnalyzerTiming], graph: &[AnalyzerTiming]) {
     eprintln!();
 }
 
+/// `--timings` with `[cache.remote]`: what the remote cache served and took
+fn print_remote_cache(stats: &RemoteStats) {
+    let errors = match stats.errors {
+        0 => String::new(),
+        n => format!(", {} error(s), local only since the first", n)
+            .yellow()
+            .to_string(),
+    };
+    eprintln!(
+        "  {} {} hit(s), {} miss(es), {} upload(s){}",
+        "Remote cache".bold(),
+        stats.hits,
+        stats.misses,
+        stats.uploads,
+        errors
+    );
+    eprintln!();
+}
+
+/// `--timings` in a watch or daemon session: what the analyzer memo
+/// answered
+fn print_memo(stats: &MemoStats) {
+    eprintln!(
+        "  {} {} hit(s), {} miss(es) ({:.0}%), {} evicted, {} invalidated",
+        "Analyzer memo".bold(),
+        stats.hits,
+        stats.misses,
+        stats.hit_rate() * 100.0,
+        stats.evictions,
+        stats.invalidations
+    );
+    eprintln!();
+}
+
+/// `--timings=full`: each analyzer's slowest files, and its peak allocation
+/// in `alloc-stats` builds
+fn print_slowest_files(domain: &[AnalyzerTiming], graph: &[AnalyzerTiming], repo_path: &Path) {
+    let all: Vec<&AnalyzerTiming> = domain
+        .iter()
+        .chain(graph.iter())
+        .filter(|t| !t.files.is_empty() || t.peak_alloc.is_some())
+        .collect();
+    if all.is_empty() {
+        return;
+    }
+
+    eprintln!("  {}", "Slowest files".bold());
+    for t in all {
+        let peak = t
+            .peak_alloc
+            .map(|bytes| format!(", peak {:.1} MiB", bytes as f64 / (1024.0 * 1024.0)))
+            .unwrap_or_default();
+        eprintln!(
+            "  {} {}",
+            t.name,
+            format!("({:.0}ms total{})", t.duration.as_secs_f64() * 1000.0, peak).dimmed()
+        );
+        for file in t.slowest_files() {
+            let path = file.file.strip_prefix(repo_path).unwrap_or(&file.file);
+            eprintln!(
+                "    {:>8}  {}",
+                format!("{:.1}ms", file.duration.as_secs_f64() * 1000.0).yellow(),
+                path.display()
+            );
+        }
+    }
+    eprintln!();
+}
+
 /// Entry-point names never flagged as dead code (diff-scoped checks).
 fn diff_dead_is_entry_point(name: &str) -> bool {
     matches!(
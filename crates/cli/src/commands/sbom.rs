@@ -0,0 +1,49 @@
+//! `revet sbom` — emit a CycloneDX or SPDX bill of materials from lockfiles
+
+use anyhow::Result;
+use colored::Colorize;
+use revet_core::{RevetConfig, Sbom, SbomFormat};
+use std::path::Path;
+
+use crate::exit;
+use crate::OutputFormat;
+
+pub fn run(
+    path: Option<&Path>,
+    format: Option<OutputFormat>,
+    output: Option<&Path>,
+    advisories: Option<&Path>,
+) -> Result<()> {
+    let format = match format {
+        None | Some(OutputFormat::Cyclonedx) => SbomFormat::CycloneDx,
+        Some(OutputFormat::SpdxJson) => SbomFormat::SpdxJson,
+        Some(other) => {
+            return Err(exit::usage(format!(
+                "`revet sbom` supports --format cyclonedx or spdx-json, not {:?}",
+                other
+            )))
+        }
+    };
+
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+    let config = RevetConfig::find_and_load(&repo_path)?;
+    let sbom = Sbom::from_repo(&repo_path, &config, advisories)?;
+    let rendered = sbom.render(format)?;
+
+    match output {
+        Some(out) => {
+            std::fs::write(out, rendered)?;
+            eprintln!(
+                "  {} {} components, {} vulnerabilities → {}",
+                "SBOM:".bold(),
+                sbom.components.len(),
+                sbom.vulnerabilities.len(),
+                out.display()
+            );
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
@@ -0,0 +1,131 @@
+//! `revet snapshot` — maintain the graph store and compare its snapshots
+//!
+//! - `gc` applies the retention policy and compacts delta chains
+//! - `changelog` renders release notes between two snapshots
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+use revet_core::diff::changelog::{Changelog, ChangelogOptions, Projects, SymbolFilter};
+use revet_core::store::{open_store, reconstruct_graph, GcReport, GraphStore, Retention};
+use revet_core::{RevetConfig, StoragePaths};
+use std::path::Path;
+use std::time::SystemTime;
+
+use crate::exit;
+use crate::{ChangelogFilter, OutputFormat};
+
+pub fn gc(
+    path: Option<&Path>,
+    keep_last: Option<usize>,
+    keep_days: Option<u64>,
+) -> Result<GcReport> {
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+    let config = RevetConfig::find_and_load(&repo_path)?;
+    let retention = Retention {
+        keep_last: keep_last.or(config.cache.keep_last),
+        keep_days: keep_days.or(config.cache.keep_days),
+    };
+    if retention.keep_last == Some(0) {
+        return Err(exit::usage(
+            "--keep-last must be at least 1 (the newest snapshot is always kept)",
+        ));
+    }
+    let Some(store) = open_store(&StoragePaths::resolve(&repo_path, &config))? else {
+        eprintln!(
+            "  {}",
+            "No graph store found. Run `revet review` to create one.".dimmed()
+        );
+        return Ok(GcReport::default());
+    };
+
+    let store = store.with_full_every(config.cache.full_snapshot_every);
+    let report = store.gc(&retention, SystemTime::now())?;
+
+    if retention.keep_last.is_none() && retention.keep_days.is_none() {
+        eprintln!(
+            "  {}",
+            "No retention policy ([cache] keep_last / keep_days) — keeping every snapshot".dimmed()
+        );
+    }
+    for name in &report.removed {
+        eprintln!("  {} {}", "removed".red(), name);
+    }
+    for name in &report.compacted {
+        eprintln!("  {} {}", "compacted".cyan(), name);
+    }
+    eprintln!(
+        "  {} snapshot(s) removed, {} compacted, {} kept",
+        report.removed.len(),
+        report.compacted.len(),
+        report.kept
+    );
+    Ok(report)
+}
+
+/// Print the changelog from snapshot `old` to `new` as markdown or JSON
+pub fn changelog(
+    path: Option<&Path>,
+    old: &str,
+    new: &str,
+    filter: ChangelogFilter,
+    churn_threshold: usize,
+    format: Option<OutputFormat>,
+) -> Result<Changelog> {
+    let markdown = match format {
+        None | Some(OutputFormat::Markdown) => true,
+        Some(OutputFormat::Json) => false,
+        Some(other) => {
+            return Err(exit::usage(format!(
+                "`revet snapshot changelog` supports --format markdown or json, not {:?}",
+                other
+            )))
+        }
+    };
+
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+    let Some(store) = open_store(&StoragePaths::discover(&repo_path))? else {
+        bail!("No graph store found. Run `revet review` to create one.");
+    };
+    let available: Vec<String> = store.snapshots()?.into_iter().map(|s| s.name).collect();
+    for name in [old, new] {
+        if !available.iter().any(|a| a == name) {
+            bail!(
+                "Snapshot '{}' not found (available: {})",
+                name,
+                if available.is_empty() {
+                    "none".to_string()
+                } else {
+                    available.join(", ")
+                }
+            );
+        }
+    }
+
+    let options = ChangelogOptions {
+        filter: match filter {
+            ChangelogFilter::All => SymbolFilter::All,
+            ChangelogFilter::PublicOnly => SymbolFilter::PublicOnly,
+        },
+        churn_threshold,
+    };
+    let old_graph = reconstruct_graph(&store, old, &repo_path)?;
+    let new_graph = reconstruct_graph(&store, new, &repo_path)?;
+    let changelog = Changelog::between(
+        old,
+        &old_graph,
+        new,
+        &new_graph,
+        &options,
+        &Projects::new(&repo_path),
+    );
+
+    if markdown {
+        print!("{}", changelog.to_markdown());
+    } else {
+        println!("{}", changelog.to_json()?);
+    }
+    Ok(changelog)
+}
@@ -1,22 +1,28 @@
 //! `revet stats` — aggregate run history into code quality metrics.
 //!
-//! Reads all run logs from `.revet-cache/runs/` and surfaces:
+//! Reads all run logs from the state directory (`.revet-cache/runs/` by default) and surfaces:
 //! - Clean run rate (% of runs with zero findings)
 //! - Week-over-week finding trend
 //! - Average findings per run by severity
 //! - Noisiest rules (most frequent across all runs)
 //! - Most suppressed rules
+//! - Would-be findings of modules in shadow mode (`rollout_percent`)
+//! - Hotspot ranking (churn × complexity) from the latest run that had one
+//! - Documentation coverage per project and least documented files, from
+//!   the latest run that measured it
 
 use anyhow::Result;
 use colored::Colorize;
-use std::collections::HashMap;
+use revet_core::StoragePaths;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::run_log::{self, RunLog};
 
 pub fn run(repo_path: &Path, last_n: Option<usize>) -> Result<()> {
-    let entries = run_log::list_runs(repo_path)?;
+    let paths = StoragePaths::discover(repo_path);
+    let entries = run_log::list_runs(&paths)?;
 
     if entries.is_empty() {
         println!(
@@ -29,11 +35,11 @@ pub fn run(repo_path: &Path, last_n: Option<usize>) -> Result<()> {
     // Load the full log for each entry (we need finding details for rule stats)
     let mut logs: Vec<RunLog> = entries
         .iter()
-        .filter_map(|e| run_log::load_run_log(repo_path, &e.id).ok())
+        .filter_map(|e| run_log::load_run_log(&paths, &e.id).ok())
         .collect();
 
     // Newest first (list_runs already returns newest-first, but reload may shuffle)
-    logs.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    logs.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
 
     // Optionally limit to last N runs
     if let Some(n) = last_n {
@@ -51,6 +57,10 @@ pub fn run(repo_path: &Path, last_n: Option<usize>) -> Result<()> {
     print_trend(now_secs, &logs);
     print_noisiest_rules(&logs);
     print_suppression_stats(&logs);
+    print_shadow_stats(&logs);
+    print_hotspots(&logs);
+    print_orphans(&logs);
+    print_doc_coverage(&logs);
 
     Ok(())
 }
@@ -178,7 +188,7 @@ fn print_noisiest_rules(logs: &[RunLog]) {
     }
 
     let mut rules: Vec<(String, usize)> = rule_counts.into_iter().collect();
-    rules.sort_by(|a, b| b.1.cmp(&a.1));
+    rules.sort_by_key(|e| std::cmp::Reverse(e.1));
     rules.truncate(5);
 
     println!("  {}", "Noisiest rules (top 5)".bold());
@@ -190,6 +200,38 @@ fn print_noisiest_rules(logs: &[RunLog]) {
     println!();
 }
 
+/// Per module in shadow mode: the runs it was shadowed in and the findings
+/// it would have added to them
+fn print_shadow_stats(logs: &[RunLog]) {
+    let mut modules: BTreeMap<&str, (usize, usize, u8)> = BTreeMap::new();
+    for log in logs {
+        for shadow in &log.summary.shadow {
+            // Newest first: keep the latest percentage
+            let entry = modules
+                .entry(&shadow.module)
+                .or_insert((0, 0, shadow.percent));
+            entry.0 += 1;
+            entry.1 += shadow.total();
+        }
+    }
+    if modules.is_empty() {
+        return;
+    }
+
+    println!("  {}", "Shadow rollout (findings not reported)".bold());
+    for (module, (runs, findings, percent)) in &modules {
+        println!(
+            "    {:<20} {} in {} run(s), {:.1} per run  {}",
+            module,
+            findings.to_string().bold(),
+            runs,
+            *findings as f64 / *runs as f64,
+            format!("rollout {}%", percent).dimmed()
+        );
+    }
+    println!();
+}
+
 fn print_suppression_stats(logs: &[RunLog]) {
     let mut sup_counts: HashMap<String, usize> = HashMap::new();
     for log in logs {
@@ -205,7 +247,7 @@ fn print_suppression_stats(logs: &[RunLog]) {
     }
 
     let mut rules: Vec<(String, usize)> = sup_counts.into_iter().collect();
-    rules.sort_by(|a, b| b.1.cmp(&a.1));
+    rules.sort_by_key(|e| std::cmp::Reverse(e.1));
     rules.truncate(5);
 
     println!("  {}", "Most suppressed rules (top 5)".bold());
@@ -226,6 +268,113 @@ fn print_suppression_stats(logs: &[RunLog]) {
     println!();
 }
 
+fn print_hotspots(logs: &[RunLog]) {
+    let Some(log) = logs.iter().find(|l| !l.hotspots.is_empty()) else {
+        return;
+    };
+
+    println!("  {}", "Hotspots (top 10 by changes × complexity)".bold());
+    println!(
+        "  {}",
+        format!("From run {}; ★ = reported as HOT", log.id).dimmed()
+    );
+    println!(
+        "    {:<4} {:<40} {:>7} {:>10} {:>7}",
+        "#", "File", "Changes", "Complexity", "Score"
+    );
+    for (i, h) in log.hotspots.iter().take(10).enumerate() {
+        let marker = if h.flagged { "★".red() } else { " ".normal() };
+        println!(
+            "    {:<4} {:<40} {:>7} {:>10} {:>7} {}",
+            i + 1,
+            h.file,
+            h.changes,
+            h.complexity,
+            h.score().to_string().bold(),
+            marker
+        );
+    }
+    println!();
+}
+
+fn print_orphans(logs: &[RunLog]) {
+    let Some(log) = logs.iter().find(|l| !l.orphans.is_empty()) else {
+        return;
+    };
+
+    println!("  {}", "Orphaned code (top 10 by lines)".bold());
+    println!(
+        "  {}",
+        format!("From run {}; ★ = complex or on a critical path", log.id).dimmed()
+    );
+    println!(
+        "    {:<4} {:<40} {:>6} {:>8} {:>9}  Owners",
+        "#", "Path", "Lines", "Departed", "Idle days"
+    );
+    for (i, o) in log.orphans.iter().take(10).enumerate() {
+        let marker = if o.escalated {
+            "★".red()
+        } else {
+            " ".normal()
+        };
+        println!(
+            "    {:<4} {:<40} {:>6} {:>7}% {:>9}  {} {}",
+            i + 1,
+            o.path,
+            o.lines.to_string().bold(),
+            o.departed,
+            o.idle_days,
+            o.owners.join(", "),
+            marker
+        );
+    }
+    println!();
+}
+
+fn print_doc_coverage(logs: &[RunLog]) {
+    let Some((log, coverage)) = logs
+        .iter()
+        .find_map(|l| l.summary.doc_coverage.as_ref().map(|c| (l, c)))
+    else {
+        return;
+    };
+
+    println!("  {}", "Documentation coverage".bold());
+    println!("  {}", format!("From run {}", log.id).dimmed());
+    println!("{}", progress_bar(coverage.total.percent, 30));
+    if coverage.projects.len() > 1 {
+        for project in &coverage.projects {
+            println!(
+                "    {:<40} {:>6.1}% {:>9}",
+                project.path,
+                project.coverage.percent,
+                format!(
+                    "{}/{}",
+                    project.coverage.documented, project.coverage.public
+                )
+            );
+        }
+    }
+    let lacking: Vec<_> = coverage
+        .files
+        .iter()
+        .filter(|f| f.coverage.documented < f.coverage.public)
+        .take(5)
+        .collect();
+    if !lacking.is_empty() {
+        println!("  {}", "Least documented files".dimmed());
+        for file in lacking {
+            println!(
+                "    {:<40} {:>6.1}% {:>9}",
+                file.path,
+                file.coverage.percent,
+                format!("{}/{}", file.coverage.documented, file.coverage.public)
+            );
+        }
+    }
+    println!();
+}
+
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
 /// Extract the rule prefix from a finding ID (e.g. "SEC" from "SEC-001").
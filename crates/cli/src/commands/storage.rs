@@ -0,0 +1,53 @@
+//! `revet storage migrate` — move state out of the repository's
+//! `.revet-cache/` into the configured data directory
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+use revet_core::storage::{MigrateReport, BASELINE_FILE};
+use revet_core::{RevetConfig, StoragePaths};
+use std::path::Path;
+
+use crate::exit::Classify;
+
+pub fn migrate(path: Option<&Path>) -> Result<MigrateReport> {
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+
+    let config = RevetConfig::find_and_load(&repo_path).usage_error()?;
+    let paths = StoragePaths::resolve(&repo_path, &config);
+    if !paths.is_relocated() && paths.baseline_path() == paths.legacy_dir().join(BASELINE_FILE) {
+        bail!(
+            "No data directory configured — set REVET_DATA_DIR, [storage] data_dir or [baseline] path first"
+        );
+    }
+    if !paths.legacy_dir().exists() {
+        eprintln!(
+            "  {}",
+            format!(
+                "Nothing to migrate — {} not found",
+                paths.legacy_dir().display()
+            )
+            .dimmed()
+        );
+        return Ok(MigrateReport::default());
+    }
+
+    let report = paths.migrate()?;
+    for name in &report.moved {
+        eprintln!("  {} {}", "moved".green(), name);
+    }
+    for name in &report.skipped {
+        eprintln!(
+            "  {} {} (already in the data directory)",
+            "kept".yellow(),
+            name
+        );
+    }
+    eprintln!(
+        "  {} item(s) moved to {}, {} kept in place",
+        report.moved.len(),
+        paths.data_dir().display(),
+        report.skipped.len()
+    );
+    Ok(report)
+}
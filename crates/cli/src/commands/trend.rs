@@ -0,0 +1,157 @@
+//! `revet trend` — baseline debt over the last days: the entries added,
+//! fixed and expired across the full runs on a branch, per owning team
+//! with `--baseline-by-owner`
+//!
+//! Each full run with a baseline records its changes since the previous
+//! one on the branch (see [`crate::run_log::BaselineRecord`]); the trend is
+//! their sum over the runs in the window.
+
+use anyhow::Result;
+use colored::Colorize;
+use revet_core::baseline::delta::{Delta, OwnerDelta};
+use revet_core::{RevetConfig, StoragePaths};
+use serde::Serialize;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::exit::{self, Classify};
+use crate::run_log;
+use crate::OutputFormat;
+
+/// The `--format json` report
+#[derive(Debug, Serialize)]
+struct TrendReport {
+    branch: String,
+    days: u64,
+    /// Full runs in the window that recorded a delta
+    runs: usize,
+    /// Per owner, with `--baseline-by-owner`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owners: Option<Vec<OwnerDelta>>,
+    total: OwnerDelta,
+}
+
+pub fn run(
+    path: Option<&Path>,
+    by_owner: bool,
+    days: u64,
+    branch: Option<&str>,
+    format: Option<OutputFormat>,
+) -> Result<()> {
+    let json = match format {
+        None | Some(OutputFormat::Terminal) => false,
+        Some(OutputFormat::Json) => true,
+        Some(other) => {
+            return Err(exit::usage(format!(
+                "`revet trend` supports --format terminal or json, not {:?}",
+                other
+            )))
+        }
+    };
+
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+    let config = RevetConfig::find_and_load(&repo_path).usage_error()?;
+    let branch = branch
+        .map(str::to_string)
+        .unwrap_or_else(|| config.general.diff_base.clone());
+
+    let paths = StoragePaths::resolve(&repo_path, &config);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let cutoff = now.saturating_sub(days * 86_400);
+
+    let mut delta = Delta::default();
+    let mut runs = 0;
+    for log in run_log::branch_history(&paths, &branch) {
+        if log.timestamp < cutoff {
+            continue;
+        }
+        // The first run recorded on the branch has nothing to count from
+        if let Some(record) = log.baseline.filter(|r| r.since.is_some()) {
+            delta.add(&record.delta);
+            runs += 1;
+        }
+    }
+
+    let report = TrendReport {
+        branch,
+        days,
+        runs,
+        total: delta.total(),
+        owners: by_owner.then(|| delta.owners.clone()),
+    };
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    Ok(())
+}
+
+fn print_report(report: &TrendReport) {
+    println!();
+    println!("  {}", "revet trend".bold().yellow());
+    println!(
+        "  {}",
+        format!(
+            "Baseline entries on {}, last {} day(s): {} full run(s)",
+            report.branch, report.days, report.runs
+        )
+        .dimmed()
+    );
+    if report.runs == 0 {
+        println!(
+            "  {}",
+            format!(
+                "No baseline changes recorded on {} yet. Run `revet review --full` on it with a baseline, twice.",
+                report.branch
+            )
+            .dimmed()
+        );
+        return;
+    }
+
+    let rows: Vec<&OwnerDelta> = report
+        .owners
+        .iter()
+        .flatten()
+        .chain(std::iter::once(&report.total))
+        .collect();
+    let width = rows
+        .iter()
+        .map(|d| d.owner.len())
+        .max()
+        .unwrap_or(0)
+        .max("Owner".len());
+    println!();
+    println!(
+        "  {:<width$}  {:>7}  {:>7}  {:>7}  {:>6}",
+        "Owner".bold(),
+        "Added".bold(),
+        "Removed".bold(),
+        "Expired".bold(),
+        "Net".bold(),
+        width = width
+    );
+    for delta in rows {
+        let net = format!("{:+}", delta.net());
+        let net = match delta.net() {
+            n if n > 0 => net.red(),
+            n if n < 0 => net.green(),
+            _ => net.normal(),
+        };
+        println!(
+            "  {:<width$}  {:>7}  {:>7}  {:>7}  {:>6}",
+            delta.owner,
+            delta.added,
+            delta.removed,
+            delta.expired,
+            net,
+            width = width
+        );
+    }
+    println!();
+}
@@ -0,0 +1,160 @@
+//! `revet upgrade-report` — readiness for a runtime upgrade: blockers and
+//! warnings of the `UPGRADE` analyzer, per runtime and module
+
+use anyhow::Result;
+use colored::Colorize;
+use revet_core::analyzer::upgrade::pack::{self, Version};
+use revet_core::analyzer::upgrade::report::{RuntimeReadiness, UpgradeReport};
+use revet_core::RevetConfig;
+use std::path::Path;
+
+use crate::exit::{self, Classify, Outcome};
+use crate::OutputFormat;
+
+pub fn run(
+    path: Option<&Path>,
+    targets: &[String],
+    format: Option<OutputFormat>,
+) -> Result<Outcome> {
+    let json = match format {
+        None | Some(OutputFormat::Terminal) => false,
+        Some(OutputFormat::Json) => true,
+        Some(other) => {
+            return Err(exit::usage(format!(
+                "`revet upgrade-report` supports --format terminal or json, not {:?}",
+                other
+            )))
+        }
+    };
+
+    let repo_path = path.unwrap_or_else(|| Path::new("."));
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+    let mut config = RevetConfig::find_and_load(&repo_path).usage_error()?;
+
+    let (packs, _) = pack::load(&config.upgrade, &repo_path);
+    let mut runtimes = Vec::new();
+    for target in targets {
+        let (runtime, version) = target
+            .split_once('=')
+            .filter(|(_, v)| Version::parse(v).is_some())
+            .ok_or_else(|| {
+                exit::usage(format!(
+                    "--target {:?} must be RUNTIME=VERSION, e.g. python=3.13",
+                    target
+                ))
+            })?;
+        if !packs.iter().any(|p| p.runtime == runtime) {
+            let known: Vec<&str> = packs.iter().map(|p| p.runtime.as_str()).collect();
+            return Err(exit::usage(format!(
+                "--target {:?}: no upgrade pack for {:?} (known: {})",
+                target,
+                runtime,
+                known.join(", ")
+            )));
+        }
+        config
+            .upgrade
+            .targets
+            .insert(runtime.to_string(), version.to_string());
+        runtimes.push(runtime.to_string());
+    }
+
+    let mut report = UpgradeReport::from_repo(&repo_path, &config).usage_error()?;
+    if !runtimes.is_empty() {
+        report.runtimes.retain(|r| runtimes.contains(&r.runtime));
+    }
+    for usage in report.runtimes.iter_mut().flat_map(|r| r.usages.iter_mut()) {
+        if let Ok(relative) = usage.file.strip_prefix(&repo_path) {
+            usage.file = relative.to_path_buf();
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    let blockers = report.runtimes.iter().map(|r| r.blockers).sum();
+    Ok(if blockers > 0 {
+        Outcome::UpgradeBlocked { blockers }
+    } else {
+        Outcome::Success
+    })
+}
+
+fn print_report(report: &UpgradeReport) {
+    if report.runtimes.is_empty() {
+        println!(
+            "  {}",
+            "No upgrade target: set one under [upgrade] or pass --target python=3.13".dimmed()
+        );
+        return;
+    }
+    for runtime in &report.runtimes {
+        print_runtime(runtime);
+    }
+}
+
+fn print_runtime(runtime: &RuntimeReadiness) {
+    let status = if runtime.ready {
+        "ready".green().bold()
+    } else {
+        format!("{} blocker(s)", runtime.blockers).red().bold()
+    };
+    println!(
+        "\n  {} → {}  {}, {} warning(s)",
+        runtime.name.bold(),
+        runtime.targets.join(", "),
+        status,
+        runtime.warnings
+    );
+    if runtime.modules.is_empty() {
+        return;
+    }
+
+    let width = runtime
+        .modules
+        .iter()
+        .map(|m| m.module.len())
+        .max()
+        .unwrap_or(0)
+        .max("module".len());
+    println!(
+        "    {:<width$}  {:>8}  {:>8}",
+        "module".dimmed(),
+        "blockers".dimmed(),
+        "warnings".dimmed(),
+        width = width
+    );
+    for module in &runtime.modules {
+        println!(
+            "    {:<width$}  {:>8}  {:>8}",
+            module.module,
+            module.blockers,
+            module.warnings,
+            width = width
+        );
+    }
+
+    let blockers: Vec<_> = runtime.usages.iter().filter(|u| u.is_blocker()).collect();
+    if blockers.is_empty() {
+        return;
+    }
+    println!("\n    {}", "Blockers:".bold());
+    for usage in blockers {
+        let instead = usage
+            .replacement
+            .as_ref()
+            .map(|r| format!(" — use {}", r))
+            .unwrap_or_default();
+        println!(
+            "    {}:{}  `{}` removed in {}{}",
+            usage.file.display(),
+            usage.line,
+            usage.api,
+            usage.version,
+            instead
+        );
+    }
+}
@@ -1,22 +1,52 @@
 //! Watch command — monitor files and re-analyze on changes
+//!
+//! One analysis runs at a time, on a worker thread. A change that arrives
+//! while a run is in flight cancels it (through its [`CancelToken`]); the
+//! partial results are dropped and a fresh run starts once the cancelled
+//! one has stopped. Changes that arrive meanwhile coalesce into that one
+//! run. The last completed results stay on screen until a newer run
+//! completes. The scheduling lives in [`Supervisor`] and [`event_loop`],
+//! which know nothing about files or output.
+//!
+//! The code graph stays in memory between runs: each run re-parses only the
+//! files whose content changed (see [`IncrementalGraph`]). It is built
+//! afresh when the `[parser]` settings change. Analyzer findings stay in
+//! memory too, per file content (see [`AnalysisMemo`]), so a run only
+//! analyzes the files that changed; the status line shows how often the
+//! memo answered.
 
 use anyhow::Result;
 use colored::Colorize;
-use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, DebouncedEventKind};
+use revet_core::storage::LEGACY_DIR;
+use revet_core::suggestion;
 use revet_core::{
-    apply_fixes, discover_files_extended, filter_findings, filter_findings_by_inline,
-    AnalyzerDispatcher, Baseline, Finding, ParserDispatcher, RevetConfig, Severity,
-    SuppressedFinding,
+    apply_fixes, discover_files_with_dotfiles, filter_findings, filter_inline_with, AnalysisMemo,
+    AnalyzerDispatcher, Baseline, CancelToken, CodeKinds, Constructs, Finding, GateConfig,
+    GeneratedFiles, IncrementalGraph, Locale, MemoStats, ParserDispatcher, Reproducibility,
+    RevetConfig, ReviewSummary, Rollout, Severity, StoragePaths, SuppressedFinding,
 };
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use super::review::{build_summary, has_extension, has_filename};
-use crate::output::{make_formatter, resolve_format};
-
-pub fn run(path: Option<&Path>, cli: &crate::Cli, debounce_ms: u64, no_clear: bool) -> Result<()> {
+use super::review::{build_summary, is_analysis_input};
+use crate::exit::{Classify, Outcome};
+use crate::output::{
+    resolve_locale, resolve_targets, Pipeline, Reporter, RunEnd, RunStart, Target,
+};
+use revet_core::config::OutputConfig;
+
+/// Watch until Ctrl-C, which is the only way out: the outcome is always
+/// [`Outcome::Interrupted`]
+pub fn run(
+    path: Option<&Path>,
+    cli: &crate::Cli,
+    debounce_ms: u64,
+    no_clear: bool,
+) -> Result<Outcome> {
     let repo_path = path.unwrap_or_else(|| Path::new("."));
     let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
 
@@ -26,11 +56,6 @@ pub fn run(path: Option<&Path>, cli: &crate::Cli, debounce_ms: u64, no_clear: bo
     );
     eprintln!();
 
-    // ── Initial run ────────────────────────────────────────────
-    run_analysis(&repo_path, cli)?;
-    eprintln!();
-    eprintln!("  {}", "Watching for changes... (Ctrl-C to stop)".dimmed());
-
     // ── Ctrl-C handler ─────────────────────────────────────────
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
@@ -39,102 +64,328 @@ pub fn run(path: Option<&Path>, cli: &crate::Cli, debounce_ms: u64, no_clear: bo
     })?;
 
     // ── Collect supported file types ───────────────────────────
-    let config = RevetConfig::find_and_load(&repo_path).unwrap_or_default();
-    let dispatcher = ParserDispatcher::new();
+    let mut config = RevetConfig::find_and_load(&repo_path).unwrap_or_default();
+    crate::profile::resolve(cli, &mut config).usage_error()?;
+    let dispatcher = ParserDispatcher::new_with_config(&config);
     let analyzer_dispatcher = AnalyzerDispatcher::new_with_config(&config);
 
-    let extensions = dispatcher.supported_extensions();
-    let extra_exts = analyzer_dispatcher.extra_extensions(&config);
-    let extra_names = analyzer_dispatcher.extra_filenames(&config);
-    let mut all_extensions: Vec<&str> = extensions;
-    for ext in &extra_exts {
-        if !all_extensions.contains(ext) {
-            all_extensions.push(ext);
+    let mut extensions: Vec<String> = dispatcher
+        .supported_extensions()
+        .into_iter()
+        .map(String::from)
+        .collect();
+    for ext in analyzer_dispatcher.extra_extensions(&config) {
+        if !extensions.iter().any(|e| e == ext) {
+            extensions.push(ext.to_string());
         }
     }
+    let owned = |names: Vec<&str>| names.into_iter().map(String::from).collect::<Vec<_>>();
+    let filenames = owned(analyzer_dispatcher.extra_filenames(&config));
+    let dotfiles = owned(analyzer_dispatcher.extra_dotfiles(&config));
+    let data_dir = StoragePaths::resolve(&repo_path, &config)
+        .data_dir()
+        .to_path_buf();
 
     // ── Set up file watcher ────────────────────────────────────
+    // Each debounced batch — however many files it touches — is one change
     let (tx, rx) = std::sync::mpsc::channel();
-    let mut debouncer = new_debouncer(Duration::from_millis(debounce_ms), tx)?;
-
-    use notify::RecursiveMode;
-    debouncer
-        .watcher()
-        .watch(repo_path.as_ref(), RecursiveMode::Recursive)?;
-
-    // ── Event loop ─────────────────────────────────────────────
-    while running.load(Ordering::SeqCst) {
-        match rx.recv_timeout(Duration::from_millis(200)) {
-            Ok(Ok(events)) => {
-                let dominated = events.iter().any(|ev| {
+    let changes = tx.clone();
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(debounce_ms),
+        move |result: DebounceEventResult| match result {
+            Ok(events) => {
+                let (extensions, filenames, dotfiles) = (
+                    as_strs(&extensions),
+                    as_strs(&filenames),
+                    as_strs(&dotfiles),
+                );
+                let relevant = events.iter().any(|ev| {
                     if ev.kind != DebouncedEventKind::Any {
                         return false;
                     }
                     let p = &ev.path;
-                    // Skip .git/ and .revet-cache/ directories
-                    if path_contains_segment(p, ".git") || path_contains_segment(p, ".revet-cache")
+                    // Skip .git/ and revet's own state
+                    if path_contains_segment(p, ".git")
+                        || path_contains_segment(p, LEGACY_DIR)
+                        || p.starts_with(&data_dir)
                     {
                         return false;
                     }
-                    has_extension(p, &all_extensions) || has_filename(p, &extra_names)
+                    is_analysis_input(p, &extensions, &filenames, &dotfiles)
                 });
-
-                if dominated {
-                    if !no_clear {
-                        clear_screen();
-                    } else {
-                        eprintln!();
-                        eprintln!("  {}", "\u{2500}".repeat(60).dimmed());
-                        eprintln!();
-                    }
-
-                    match run_analysis(&repo_path, cli) {
-                        Ok(_) => {}
-                        Err(e) => {
-                            eprintln!("  {}: {}", "analysis error".red(), e);
-                        }
-                    }
-                    eprintln!();
-                    eprintln!("  {}", "Watching for changes... (Ctrl-C to stop)".dimmed());
+                if relevant {
+                    let _ = changes.send(Event::Changed);
                 }
             }
-            Ok(Err(errs)) => {
+            Err(errs) => {
                 eprintln!("  {}: {:?}", "watch error".red(), errs);
             }
-            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
-                // Normal timeout — check if we should keep running
+        },
+    )?;
+
+    use notify::RecursiveMode;
+    debouncer
+        .watcher()
+        .watch(repo_path.as_ref(), RecursiveMode::Recursive)?;
+
+    // ── Event loop (the initial run is the first change) ───────
+    tx.send(Event::Changed)?;
+    let graph = Mutex::new(None);
+    event_loop(
+        rx,
+        tx,
+        |token| analyze(&repo_path, cli, &graph, token),
+        |notice| present(notice, &repo_path, cli, no_clear),
+        &running,
+    );
+
+    eprintln!();
+    eprintln!("  {}", "Stopped watching.".bold());
+    Ok(Outcome::Interrupted)
+}
+
+// ── Scheduling ───────────────────────────────────────────────────
+
+/// Input to the watch loop
+pub enum Event<T> {
+    /// A batch of changes that calls for a new analysis
+    Changed,
+    /// Analysis `run` returned
+    Finished { run: u64, result: Result<T> },
+}
+
+/// What the watch loop reports, in order
+#[derive(Debug)]
+pub enum Notice<T> {
+    /// Analysis `run` started; earlier results stay on screen
+    Started {
+        run: u64,
+    },
+    /// Analysis `run` completed and is the latest state of the repository
+    Completed {
+        run: u64,
+        result: T,
+    },
+    /// Analysis `run` was overtaken by a newer change; its results are dropped
+    Superseded {
+        run: u64,
+    },
+    Failed {
+        run: u64,
+        error: anyhow::Error,
+    },
+}
+
+/// Step taken by the watch loop
+pub enum Action<T> {
+    /// Start analysis `run`, which stops early once `token` is cancelled
+    Start {
+        run: u64,
+        token: CancelToken,
+    },
+    Notify(Notice<T>),
+}
+
+/// Watch-mode state machine: at most one analysis in flight, superseded
+/// by newer changes, with changes during a run coalesced into the next one
+#[derive(Debug, Default)]
+pub struct Supervisor {
+    runs: u64,
+    current: Option<(u64, CancelToken)>,
+    pending: bool,
+}
+
+impl Supervisor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether an analysis is in flight
+    pub fn is_running(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// A change arrived: start a run, or cancel the one in flight and
+    /// queue a fresh run for when it has stopped
+    pub fn on_change<T>(&mut self) -> Option<Action<T>> {
+        match &self.current {
+            Some((_, token)) => {
+                token.cancel();
+                self.pending = true;
+                None
             }
-            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
-                break;
+            None => Some(self.start()),
+        }
+    }
+
+    /// Run `run` returned. A cancelled run is reported as superseded even
+    /// if it completed, so results older than the latest change are never
+    /// shown.
+    pub fn on_finished<T>(&mut self, run: u64, result: Result<T>) -> Vec<Action<T>> {
+        let Some((current, token)) = self.current.take() else {
+            return Vec::new();
+        };
+        if current != run {
+            self.current = Some((current, token));
+            return Vec::new();
+        }
+
+        let notice = if token.is_cancelled() {
+            Notice::Superseded { run }
+        } else {
+            match result {
+                Ok(result) => Notice::Completed { run, result },
+                Err(error) => Notice::Failed { run, error },
             }
+        };
+        let mut actions = vec![Action::Notify(notice)];
+        if std::mem::take(&mut self.pending) {
+            actions.push(self.start());
         }
+        actions
     }
 
-    eprintln!();
-    eprintln!("  {}", "Stopped watching.".bold());
-    Ok(())
+    /// Stop watching: cancel the run in flight
+    pub fn shutdown(&mut self) {
+        if let Some((_, token)) = &self.current {
+            token.cancel();
+        }
+        self.pending = false;
+    }
+
+    fn start<T>(&mut self) -> Action<T> {
+        self.runs += 1;
+        let token = CancelToken::new();
+        self.current = Some((self.runs, token.clone()));
+        Action::Start {
+            run: self.runs,
+            token,
+        }
+    }
+}
+
+/// Drive a [`Supervisor`] until `running` is cleared: read [`Event`]s from
+/// `events`, run `analyze` on a worker thread for each started run (its
+/// result comes back through `sender`), and hand every [`Notice`] to
+/// `present`. Returns once the last worker has stopped.
+pub fn event_loop<T, A, P>(
+    events: Receiver<Event<T>>,
+    sender: Sender<Event<T>>,
+    analyze: A,
+    mut present: P,
+    running: &AtomicBool,
+) where
+    T: Send,
+    A: Fn(&CancelToken) -> Result<T> + Sync,
+    P: FnMut(Notice<T>),
+{
+    let mut supervisor = Supervisor::new();
+    std::thread::scope(|scope| {
+        let analyze = &analyze;
+        while running.load(Ordering::SeqCst) {
+            let actions = match events.recv_timeout(Duration::from_millis(100)) {
+                Ok(Event::Changed) => supervisor.on_change().into_iter().collect(),
+                Ok(Event::Finished { run, result }) => supervisor.on_finished(run, result),
+                // Normal timeout — check if we should keep running
+                Err(RecvTimeoutError::Timeout) => Vec::new(),
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+            for action in actions {
+                match action {
+                    Action::Start { run, token } => {
+                        present(Notice::Started { run });
+                        let sender = sender.clone();
+                        scope.spawn(move || {
+                            let result = analyze(&token);
+                            let _ = sender.send(Event::Finished { run, result });
+                        });
+                    }
+                    Action::Notify(notice) => present(notice),
+                }
+            }
+        }
+        supervisor.shutdown();
+    });
+}
+
+// ── Analysis ─────────────────────────────────────────────────────
+
+/// A completed analysis, ready to print
+pub struct WatchRun {
+    targets: Vec<Target>,
+    output: OutputConfig,
+    locale: Locale,
+    reproducibility: Reproducibility,
+    findings: Vec<Finding>,
+    suppressed: Vec<SuppressedFinding>,
+    /// `None` when no files were discovered
+    summary: Option<ReviewSummary>,
+    /// The gate / fail-on result
+    passed: bool,
+    elapsed: Duration,
+    /// What the analyzer memo did during the run; `None` with `[performance]
+    /// memo_mb = 0`
+    memo: Option<MemoStats>,
+}
+
+/// The code graph kept between runs
+struct KeptGraph {
+    /// The `[parser]` settings it was built with, serialized
+    parser: String,
+    graph: IncrementalGraph,
 }
 
-fn run_analysis(repo_path: &Path, cli: &crate::Cli) -> Result<()> {
+/// Analyze the repository without printing; stops with
+/// [`Cancelled`](revet_core::Cancelled) between files and analyzers once
+/// `token` is cancelled
+fn analyze(
+    repo_path: &Path,
+    cli: &crate::Cli,
+    graph: &Mutex<Option<KeptGraph>>,
+    token: &CancelToken,
+) -> Result<WatchRun> {
     let start = Instant::now();
 
     // ── 1. Config (re-load each run) ──────────────────────────
-    let config = match RevetConfig::find_and_load(repo_path) {
+    let mut config = match RevetConfig::find_and_load(repo_path) {
         Ok(c) => c,
         Err(e) => {
             eprintln!("  {}: {}", "config error".red(), e);
             RevetConfig::default()
         }
     };
-    let format = resolve_format(cli, &config);
+    let settings = crate::profile::resolve(cli, &mut config)?;
+    let mut run = WatchRun {
+        targets: resolve_targets(cli, &config)?,
+        output: config.output.clone(),
+        locale: resolve_locale(&config),
+        reproducibility: settings.reproducibility(),
+        findings: Vec::new(),
+        suppressed: Vec::new(),
+        summary: None,
+        passed: true,
+        elapsed: Duration::ZERO,
+        memo: None,
+    };
 
     // ── 2. File discovery (full scan) ─────────────────────────
-    let dispatcher = ParserDispatcher::new();
-    let analyzer_dispatcher = AnalyzerDispatcher::new_with_config(&config);
+    let dispatcher = ParserDispatcher::new_with_config(&config);
+    let rollout = Rollout::resolve(&config, repo_path, settings.rollout_sampling);
+    run.reproducibility = run
+        .reproducibility
+        .with_rollout(&rollout)
+        .with_language_targets(repo_path, &config);
+    // Resized when `memo_mb` changes; emptied by any other config change
+    let memo = AnalysisMemo::start_session(&config);
+    let memo_before = memo.as_ref().map(AnalysisMemo::stats);
+    let analyzer_dispatcher = AnalyzerDispatcher::new_with_config(&config)
+        .with_rollout(rollout)
+        .with_memo(memo.clone());
 
     let extensions = dispatcher.supported_extensions();
     let extra_exts = analyzer_dispatcher.extra_extensions(&config);
     let extra_names = analyzer_dispatcher.extra_filenames(&config);
+    let extra_dotfiles = analyzer_dispatcher.extra_dotfiles(&config);
     let mut all_extensions: Vec<&str> = extensions;
     for ext in &extra_exts {
         if !all_extensions.contains(ext) {
@@ -142,36 +393,27 @@ fn run_analysis(repo_path: &Path, cli: &crate::Cli) -> Result<()> {
         }
     }
 
-    eprint!("  Discovering files... ");
-    let files = discover_files_extended(
+    let files = discover_files_with_dotfiles(
         repo_path,
         &all_extensions,
         &extra_names,
+        &extra_dotfiles,
         &config.ignore.paths,
     )?;
-    eprintln!("{} ({} files)", "done".green(), files.len());
-
     if files.is_empty() {
-        let mut out = make_formatter(format, repo_path, false);
-        out.write_no_files(start.elapsed());
-        out.finalize();
-        return Ok(());
+        run.elapsed = start.elapsed();
+        return Ok(run);
     }
-
-    // ── 3. Parse (parallel) ────────────────────────────────────
-    eprint!("  Building code graph... ");
-    let graph_start = Instant::now();
-
-    let (graph, parse_errors) = dispatcher.parse_files_parallel(&files, repo_path.to_path_buf());
-
-    let node_count: usize = graph.nodes().count();
-    eprintln!(
-        "{} \u{2014} {} files, {} nodes ({:.1}s)",
-        "done".green(),
-        files.len(),
-        node_count,
-        graph_start.elapsed().as_secs_f64()
-    );
+    token.check()?;
+    let generated = GeneratedFiles::from_config(&config.files).classify(&files);
+    let analysis_files = generated.analysis_files(&files);
+
+    // ── 3. Parse (changed files only) ─────────────────────────
+    let (node_count, parse_errors, constructs) = if settings.graph {
+        update_graph(graph, &dispatcher, &config, &files, repo_path, token)?
+    } else {
+        (0, Vec::new(), Constructs::default())
+    };
 
     // ── 4. Domain analyzers ───────────────────────────────────
     let mut findings: Vec<Finding> = Vec::new();
@@ -190,64 +432,197 @@ fn run_analysis(repo_path: &Path, cli: &crate::Cli) -> Result<()> {
         });
     }
 
-    eprint!("  Running domain analyzers... ");
-    let analyzer_start = Instant::now();
-    let analyzer_findings = analyzer_dispatcher.run_all_parallel(&files, repo_path, &config);
-    let analyzer_count = analyzer_findings.len();
-    findings.extend(analyzer_findings);
-    eprintln!(
-        "{} \u{2014} {} finding(s) ({:.1}s)",
-        "done".green(),
-        analyzer_count,
-        analyzer_start.elapsed().as_secs_f64()
-    );
+    let analyzer_findings = analyzer_dispatcher.run_all_parallel_cancellable(
+        &analysis_files,
+        repo_path,
+        &config,
+        token,
+    )?;
+    run.memo = memo
+        .zip(memo_before)
+        .map(|(memo, before)| memo.stats().since(&before));
+    let analyzer_findings = generated.apply(analyzer_findings, repo_path);
+    findings.extend(CodeKinds::from_config(&config.files).apply(analyzer_findings, repo_path));
+    findings.retain(|f| settings.keeps(f));
+    let (kept, shadow) = analyzer_dispatcher.split_shadow(findings);
+    findings = kept;
+    suggestion::verify(&mut findings, repo_path);
 
     // ── 5. Apply fixes ────────────────────────────────────────
+    // Never edit files on behalf of a run that has been superseded
     if cli.fix {
-        eprint!("  Applying fixes... ");
-        match apply_fixes(&findings) {
-            Ok(report) => eprintln!(
-                "{} ({} applied, {} suggestion-only)",
-                "done".green(),
-                report.applied,
-                report.skipped
-            ),
-            Err(e) => eprintln!("{}: {}", "failed".red(), e),
+        token.check()?;
+        let mut journal = cli.journal(repo_path, &StoragePaths::resolve(repo_path, &config));
+        if let Err(e) = apply_fixes(&findings, &mut journal) {
+            eprintln!("  {}: {}", "fixes failed".red(), e);
+        }
+        if let Err(e) = journal.finish() {
+            eprintln!("  warn: audit log not written: {:#}", e);
         }
     }
 
     // ── 6. Inline suppression ─────────────────────────────────
-    let mut all_suppressed: Vec<SuppressedFinding> = Vec::new();
-    let (new_findings, inline_suppressed) = filter_findings_by_inline(findings);
-    findings = new_findings;
-    all_suppressed.extend(inline_suppressed);
+    let inline = filter_inline_with(findings, &constructs);
+    findings = inline.kept;
+    run.suppressed.extend(inline.suppressed);
 
     // ── 7. Baseline suppression ───────────────────────────────
     if !cli.no_baseline {
-        if let Some(baseline) = Baseline::load(repo_path)? {
+        if let Some(baseline) = Baseline::load_at(&StoragePaths::resolve(repo_path, &config))? {
             let (new_findings, baseline_suppressed) =
                 filter_findings(findings, &baseline, repo_path);
             findings = new_findings;
-            all_suppressed.extend(baseline_suppressed);
+            run.suppressed.extend(baseline_suppressed);
         }
     }
 
-    // ── 8. Output ─────────────────────────────────────────────
-    let summary = build_summary(&findings, &files, node_count);
+    // ── 8. Session ignores (`revet ignore`) ───────────────────
+    let paths = StoragePaths::resolve(repo_path, &config);
+    let session_ignored = super::ignore::active(cli, &paths).map(|ignores| {
+        super::ignore::apply(&ignores, &mut findings, &mut run.suppressed, repo_path)
+    });
+
+    let mut summary = build_summary(&findings, &files, node_count);
+    summary.session_ignored = session_ignored;
+    summary.shadow =
+        analyzer_dispatcher.shadow_summary(&filter_inline_with(shadow, &constructs).kept);
+    let gate = cli
+        .gate
+        .as_deref()
+        .map(GateConfig::from_flag)
+        .unwrap_or_else(|| config.gate.clone());
+    run.passed = Outcome::from_threshold(&summary, &gate, &settings.fail_on).is_success();
+    run.summary = Some(summary);
+    run.findings = findings;
+    run.elapsed = start.elapsed();
+    Ok(run)
+}
+
+/// Bring the kept graph up to date with `files`, building it on the first
+/// run and after the `[parser]` settings changed. Returns its node count,
+/// parse errors and the functions and classes `revet-ignore:block` covers.
+fn update_graph(
+    kept: &Mutex<Option<KeptGraph>>,
+    dispatcher: &ParserDispatcher,
+    config: &RevetConfig,
+    files: &[PathBuf],
+    repo_path: &Path,
+    token: &CancelToken,
+) -> Result<(usize, Vec<String>, Constructs)> {
+    let parser = serde_json::to_string(&config.parser)?;
+    // A run that panicked mid-update may have left the graph half-swapped
+    let mut kept = kept.lock().unwrap_or_else(|poisoned| {
+        let mut kept = poisoned.into_inner();
+        *kept = None;
+        kept
+    });
+    // A cancelled update or build leaves the previous graph in place
+    let graph = match kept.as_mut() {
+        Some(current) if current.parser == parser => {
+            current.graph.update(dispatcher, files, token)?;
+            &current.graph
+        }
+        _ => {
+            let graph = IncrementalGraph::build(dispatcher, files, repo_path.to_path_buf(), token)?;
+            &kept.insert(KeptGraph { parser, graph }).graph
+        }
+    };
+    Ok((
+        graph.graph().nodes().count(),
+        graph.errors(),
+        Constructs::from_graph(graph.graph()),
+    ))
+}
+
+// ── Output ───────────────────────────────────────────────────────
 
-    let mut out = make_formatter(format, repo_path, cli.show_suppressed);
-    for f in &findings {
-        out.write_finding(f, repo_path);
+fn present(notice: Notice<WatchRun>, repo_path: &Path, cli: &crate::Cli, no_clear: bool) {
+    match notice {
+        // The initial run has nothing on screen to keep
+        Notice::Started { run: 1 } => eprintln!("  {}", "Analyzing...".dimmed()),
+        Notice::Started { .. } => {
+            eprintln!("  {}", "Change detected — re-analyzing...".dimmed());
+        }
+        Notice::Superseded { .. } => {
+            eprintln!("  {}", "\u{21b7} superseded by a newer change".dimmed());
+        }
+        Notice::Completed { result, .. } => {
+            if !no_clear {
+                clear_screen();
+            } else {
+                eprintln!();
+                eprintln!("  {}", "\u{2500}".repeat(60).dimmed());
+                eprintln!();
+            }
+            write_run(&result, repo_path, cli);
+            eprintln!();
+            eprintln!("  {}", status_line(result.memo.as_ref()).dimmed());
+        }
+        Notice::Failed { error, .. } => {
+            eprintln!("  {}: {}", "analysis error".red(), error);
+        }
+    }
+}
+
+/// What is shown under the results while waiting for the next change
+pub fn status_line(memo: Option<&MemoStats>) -> String {
+    let waiting = "Watching for changes... (Ctrl-C to stop)";
+    match memo {
+        Some(memo) => format!(
+            "{} · memo {:.0}% hits, {} evicted",
+            waiting,
+            memo.hit_rate() * 100.0,
+            memo.evictions
+        ),
+        None => waiting.to_string(),
+    }
+}
+
+fn write_run(run: &WatchRun, repo_path: &Path, cli: &crate::Cli) {
+    let Some(summary) = &run.summary else {
+        let mut out = Pipeline::new(&run.targets, repo_path, false, false, run.locale);
+        out.end_run(&RunEnd::no_files(run.elapsed));
+        finish(&mut out, run);
+        return;
+    };
+
+    let mut out = Pipeline::new(
+        &run.targets,
+        repo_path,
+        cli.show_suppressed,
+        cli.verbose,
+        run.locale,
+    );
+    out.begin_run(&RunStart {
+        reproducibility: Some(&run.reproducibility),
+        ..RunStart::default()
+    });
+    for f in &run.findings {
+        out.report_finding(f, repo_path);
     }
     if cli.show_suppressed {
-        for sf in &all_suppressed {
-            out.write_suppressed(sf, repo_path);
+        for sf in &run.suppressed {
+            out.report_suppressed(sf, repo_path);
         }
     }
-    out.write_summary(&summary, &all_suppressed, start.elapsed(), None);
-    out.finalize();
+    out.end_run(&RunEnd {
+        elapsed: run.elapsed,
+        passed: run.passed,
+        ..RunEnd::new(summary, &run.suppressed)
+    });
+    finish(&mut out, run);
+}
+
+/// Print the run, or write its reports; a failed write is reported and
+/// watching goes on
+fn finish(out: &mut Pipeline, run: &WatchRun) {
+    if let Err(e) = out.finish(&run.output) {
+        eprintln!("  {}: {:#}", "output error".red(), e);
+    }
+}
 
-    Ok(())
+fn as_strs(names: &[String]) -> Vec<&str> {
+    names.iter().map(String::as_str).collect()
 }
 
 fn path_contains_segment(path: &Path, segment: &str) -> bool {
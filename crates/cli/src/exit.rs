@@ -0,0 +1,237 @@
+//! Exit-code contract
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0    | Success |
+//! | 1    | Findings exceeded `--fail-on` / the gate, a `fail` policy was violated, a check did not pass, or some tracker calls of `revet issues sync` failed |
+//! | 2    | Usage or config error: bad flags, invalid `.revet.toml`, unknown module or profile |
+//! | 3    | Environment error: not a git repository, missing base ref, `[performance] max_memory_mb` exceeded |
+//! | 4    | Internal error: a panic, or any error not classified above |
+//! | 5    | `--assert-hash`: the project's inputs changed since the digest was taken |
+//! | 130  | Interrupted (Ctrl-C) |
+//!
+//! Commands return an [`Outcome`] (codes 0, 1 and 5) or an error; `main` maps
+//! both to the process exit code in one place. Errors are classified where
+//! they arise with [`Classify`]; an unclassified error is internal.
+
+use anyhow::Result;
+use revet_core::{Cancelled, GateConfig, ReviewSummary};
+use std::fmt;
+
+/// How a command finished without an error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    /// `count` findings at or above `threshold` (a `--fail-on` level, or
+    /// `gate` for a quality gate)
+    FindingsExceedThreshold {
+        count: usize,
+        threshold: String,
+    },
+    /// Violated `[[policy]]` entries with `action = "fail"`
+    PolicyFailed {
+        policies: Vec<String>,
+    },
+    /// `revet multi`: repositories that failed or errored
+    ReposFailed {
+        count: usize,
+    },
+    /// `revet issues sync`: tracker calls that failed
+    IssuesFailed {
+        failures: usize,
+    },
+    /// `revet debug replay` did not reproduce the captured finding
+    NotReproduced,
+    /// `revet audit verify`: the audit log's hash chain is broken
+    AuditTampered {
+        problems: usize,
+    },
+    /// `revet upgrade-report`: uses of removed APIs block the upgrade
+    UpgradeBlocked {
+        blockers: usize,
+    },
+    /// `--assert-hash`: the project's inputs now hash to `digest`
+    StaleInputs {
+        digest: String,
+    },
+    /// Stopped by Ctrl-C
+    Interrupted,
+}
+
+impl Outcome {
+    /// The outcome of findings against the quality gate, or the fail-on
+    /// threshold when no gate is set
+    pub fn from_threshold(summary: &ReviewSummary, gate: &GateConfig, fail_on: &str) -> Self {
+        if !gate.is_empty() {
+            if !summary.exceeds_gate(gate) {
+                return Outcome::Success;
+            }
+            let count = [
+                (gate.error_max, summary.errors),
+                (gate.warning_max, summary.warnings),
+                (gate.info_max, summary.info),
+            ]
+            .iter()
+            .filter(|(max, _)| max.is_some())
+            .map(|(_, n)| n)
+            .sum();
+            return Outcome::FindingsExceedThreshold {
+                count,
+                threshold: "gate".to_string(),
+            };
+        }
+
+        if !summary.exceeds_threshold(fail_on) {
+            return Outcome::Success;
+        }
+        let (count, threshold) = match fail_on {
+            "warning" => (summary.errors + summary.warnings, "warning"),
+            "info" => (summary.errors + summary.warnings + summary.info, "info"),
+            _ => (summary.errors, "error"),
+        };
+        Outcome::FindingsExceedThreshold {
+            count,
+            threshold: threshold.to_string(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        *self == Outcome::Success
+    }
+
+    pub fn code(&self) -> i32 {
+        match self {
+            Outcome::Success => 0,
+            Outcome::Interrupted => ErrorKind::Interrupted.code(),
+            Outcome::StaleInputs { .. } => 5,
+            _ => 1,
+        }
+    }
+
+    /// The `--print-exit-reason` line
+    pub fn reason(&self) -> String {
+        let detail = match self {
+            Outcome::Success => "reason=success".to_string(),
+            Outcome::FindingsExceedThreshold { count, threshold } => format!(
+                "reason=findings_exceed_threshold count={} threshold={}",
+                count, threshold
+            ),
+            Outcome::PolicyFailed { policies } => {
+                format!("reason=policy_failed policies={}", policies.join(","))
+            }
+            Outcome::ReposFailed { count } => format!("reason=repos_failed count={}", count),
+            Outcome::IssuesFailed { failures } => {
+                format!("reason=issues_failed failures={}", failures)
+            }
+            Outcome::NotReproduced => "reason=not_reproduced".to_string(),
+            Outcome::AuditTampered { problems } => {
+                format!("reason=audit_tampered problems={}", problems)
+            }
+            Outcome::UpgradeBlocked { blockers } => {
+                format!("reason=upgrade_blocked blockers={}", blockers)
+            }
+            Outcome::StaleInputs { digest } => format!("reason=stale_inputs digest={}", digest),
+            Outcome::Interrupted => "reason=interrupted".to_string(),
+        };
+        format!("exit={} {}", self.code(), detail)
+    }
+}
+
+/// Class of a failed command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Usage,
+    Environment,
+    Internal,
+    Interrupted,
+}
+
+impl ErrorKind {
+    /// Classify `err` by the first [`RevetError`] in its chain; a
+    /// [`Cancelled`] run was interrupted, anything else is internal
+    pub fn of(err: &anyhow::Error) -> Self {
+        for cause in err.chain() {
+            if let Some(e) = cause.downcast_ref::<RevetError>() {
+                return e.kind;
+            }
+            if cause.is::<Cancelled>() {
+                return ErrorKind::Interrupted;
+            }
+        }
+        ErrorKind::Internal
+    }
+
+    pub fn code(self) -> i32 {
+        match self {
+            ErrorKind::Usage => 2,
+            ErrorKind::Environment => 3,
+            ErrorKind::Internal => 4,
+            ErrorKind::Interrupted => 130,
+        }
+    }
+
+    /// The `--print-exit-reason` line
+    pub fn reason(self) -> String {
+        let reason = match self {
+            ErrorKind::Usage => "usage_error",
+            ErrorKind::Environment => "environment_error",
+            ErrorKind::Internal => "internal_error",
+            ErrorKind::Interrupted => "interrupted",
+        };
+        format!("exit={} reason={}", self.code(), reason)
+    }
+}
+
+/// An error tagged with its [`ErrorKind`]
+#[derive(Debug)]
+pub struct RevetError {
+    pub kind: ErrorKind,
+    error: anyhow::Error,
+}
+
+impl RevetError {
+    pub fn new(kind: ErrorKind, error: impl Into<anyhow::Error>) -> Self {
+        Self {
+            kind,
+            error: error.into(),
+        }
+    }
+}
+
+impl fmt::Display for RevetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#}", self.error)
+    }
+}
+
+impl std::error::Error for RevetError {}
+
+/// A usage error from a message, for flag combinations `clap` cannot check
+pub fn usage(message: impl fmt::Display) -> anyhow::Error {
+    RevetError::new(ErrorKind::Usage, anyhow::anyhow!("{}", message)).into()
+}
+
+/// An environment error from a message, for limits the run hit on the
+/// machine it ran on
+pub fn environment(message: impl fmt::Display) -> anyhow::Error {
+    RevetError::new(ErrorKind::Environment, anyhow::anyhow!("{}", message)).into()
+}
+
+/// Tag the error of a `Result` with its [`ErrorKind`], like
+/// [`anyhow::Context`]
+pub trait Classify<T> {
+    /// Bad flags or configuration: exit 2
+    fn usage_error(self) -> Result<T>;
+    /// Missing or unusable environment (git repository, base ref): exit 3
+    fn environment_error(self) -> Result<T>;
+}
+
+impl<T, E: Into<anyhow::Error>> Classify<T> for std::result::Result<T, E> {
+    fn usage_error(self) -> Result<T> {
+        self.map_err(|e| RevetError::new(ErrorKind::Usage, e).into())
+    }
+
+    fn environment_error(self) -> Result<T> {
+        self.map_err(|e| RevetError::new(ErrorKind::Environment, e).into())
+    }
+}
@@ -0,0 +1,171 @@
+//! GitHub Issues tracker
+//!
+//! - `GITHUB_TOKEN` — token with `issues: write`
+//! - `GITHUB_REPOSITORY` — `owner/repo`, unless `[issues.github] repository`
+//! - `GITHUB_API_URL` — default `https://api.github.com`
+//!
+//! Revet's issues carry the `[issues] label`; open ones are listed by it,
+//! 100 per page, following the `Link: rel="next"` header.
+
+use anyhow::{Context, Result};
+use revet_core::IssuesConfig;
+use serde_json::{json, Value};
+
+use super::http::Http;
+use super::{marker_of, Issue, NewIssue, Tracker};
+use crate::exit;
+
+pub struct GithubTracker {
+    http: Http,
+    api_url: String,
+    repository: String,
+    token: String,
+    label: String,
+}
+
+impl GithubTracker {
+    /// The tracker for `config`, credentials from the environment
+    pub fn from_env(config: &IssuesConfig) -> Result<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let token = var("GITHUB_TOKEN")
+            .ok_or_else(|| exit::environment("--tracker github needs GITHUB_TOKEN"))?;
+        let repository = config
+            .github
+            .repository
+            .clone()
+            .or_else(|| var("GITHUB_REPOSITORY"))
+            .ok_or_else(|| {
+                exit::usage(
+                    "--tracker github needs [issues.github] repository or GITHUB_REPOSITORY",
+                )
+            })?;
+        Ok(Self {
+            http: Http::new()?,
+            api_url: var("GITHUB_API_URL").unwrap_or_else(|| "https://api.github.com".to_string()),
+            repository,
+            token,
+            label: config.label.clone(),
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!(
+            "{}/repos/{}/{}",
+            self.api_url.trim_end_matches('/'),
+            self.repository,
+            path
+        )
+    }
+
+    fn request(
+        &self,
+        what: &str,
+        build: impl Fn(&reqwest::blocking::Client) -> reqwest::blocking::RequestBuilder,
+    ) -> Result<reqwest::blocking::Response> {
+        self.http.send(what, |client| {
+            build(client)
+                .bearer_auth(&self.token)
+                .header("Accept", "application/vnd.github+json")
+                .header("X-GitHub-Api-Version", "2022-11-28")
+        })
+    }
+}
+
+impl Tracker for GithubTracker {
+    fn name(&self) -> &str {
+        "GitHub"
+    }
+
+    fn open_issues(&self) -> Result<Vec<Issue>> {
+        let mut issues = Vec::new();
+        let mut next = Some(self.url("issues"));
+        let mut query = Some([
+            ("state", "open".to_string()),
+            ("labels", self.label.clone()),
+            ("per_page", "100".to_string()),
+        ]);
+        while let Some(url) = next.take() {
+            // The first page takes the query; `next` links carry their own
+            let params = query.take();
+            let resp = self.request("list issues", |client| {
+                let req = client.get(&url);
+                match &params {
+                    Some(params) => req.query(params),
+                    None => req,
+                }
+            })?;
+            next = next_link(resp.headers());
+            let page: Vec<Value> = resp.json().context("list issues: unexpected answer")?;
+            issues.extend(page.iter().filter_map(issue_of));
+        }
+        Ok(issues)
+    }
+
+    fn create(&self, issue: &NewIssue) -> Result<Issue> {
+        let body = json!({
+            "title": issue.title,
+            "body": issue.body,
+            "labels": [self.label],
+        });
+        let resp = self.request("create issue", |client| {
+            client.post(self.url("issues")).json(&body)
+        })?;
+        let created: Value = resp.json().context("create issue: unexpected answer")?;
+        Ok(Issue {
+            key: format!("#{}", created["number"]),
+            title: issue.title.clone(),
+            fingerprint: issue.fingerprint.clone(),
+            url: created["html_url"].as_str().unwrap_or_default().to_string(),
+        })
+    }
+
+    fn close(&self, issue: &Issue, comment: &str) -> Result<()> {
+        let number = issue.key.trim_start_matches('#');
+        let what = format!("close issue {}", issue.key);
+        let body = json!({ "body": comment });
+        self.request(&what, |client| {
+            client
+                .post(self.url(&format!("issues/{}/comments", number)))
+                .json(&body)
+        })?;
+        let body = json!({ "state": "closed", "state_reason": "completed" });
+        self.request(&what, |client| {
+            client
+                .patch(self.url(&format!("issues/{}", number)))
+                .json(&body)
+        })?;
+        Ok(())
+    }
+}
+
+/// The issue of an item of a listing; `None` for pull requests (which the
+/// issues API lists too) and issues without a marker
+fn issue_of(item: &Value) -> Option<Issue> {
+    if item.get("pull_request").is_some() {
+        return None;
+    }
+    let fingerprint = marker_of(item["body"].as_str()?)?;
+    Some(Issue {
+        key: format!("#{}", item["number"].as_u64()?),
+        title: item["title"].as_str().unwrap_or_default().to_string(),
+        fingerprint: fingerprint.to_string(),
+        url: item["html_url"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+/// The `rel="next"` URL of a `Link` header
+fn next_link(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    let link = headers.get("link")?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|p| p.trim() == "rel=\"next\"")
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
@@ -0,0 +1,89 @@
+//! Requests to tracker APIs, waiting out rate limits
+//!
+//! A `429`, or a `403` with `x-ratelimit-remaining: 0` (GitHub's primary
+//! limit), is retried after the wait the server asks for: `Retry-After`
+//! seconds, else until `x-ratelimit-reset`, else a doubling backoff. A wait
+//! longer than [`MAX_WAIT`] is not sat out; the request fails instead.
+
+use anyhow::{bail, Context, Result};
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::StatusCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Retries of a rate-limited request
+const MAX_RETRIES: u32 = 4;
+
+/// Longest wait for a rate limit to reset
+const MAX_WAIT: Duration = Duration::from_secs(60);
+
+pub struct Http {
+    client: Client,
+}
+
+impl Http {
+    pub fn new() -> Result<Self> {
+        let client = Client::builder()
+            .user_agent(format!("revet/{}", revet_core::VERSION))
+            .timeout(Duration::from_secs(30))
+            .build()?;
+        Ok(Self { client })
+    }
+
+    /// Send the request `build` makes, retrying while rate-limited; an
+    /// answer other than 2xx is an error with its status and body
+    pub fn send(&self, what: &str, build: impl Fn(&Client) -> RequestBuilder) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            let resp = build(&self.client)
+                .send()
+                .with_context(|| format!("{} failed", what))?;
+            if resp.status().is_success() {
+                return Ok(resp);
+            }
+            if let Some(wait) = rate_limit_wait(&resp, attempt) {
+                if attempt >= MAX_RETRIES {
+                    bail!("{}: still rate-limited after {} retries", what, MAX_RETRIES);
+                }
+                if wait > MAX_WAIT {
+                    bail!(
+                        "{}: rate-limited for another {}s; try again later",
+                        what,
+                        wait.as_secs()
+                    );
+                }
+                std::thread::sleep(wait);
+                attempt += 1;
+                continue;
+            }
+            let status = resp.status();
+            let text = resp.text().unwrap_or_default();
+            bail!("{}: {} {}", what, status, text.trim());
+        }
+    }
+}
+
+/// How long to wait before retrying `resp`, when it is a rate limit
+fn rate_limit_wait(resp: &Response, attempt: u32) -> Option<Duration> {
+    let header = |name: &str| {
+        resp.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+    };
+    let limited = resp.status() == StatusCode::TOO_MANY_REQUESTS
+        || (resp.status() == StatusCode::FORBIDDEN && header("x-ratelimit-remaining") == Some("0"));
+    if !limited {
+        return None;
+    }
+    if let Some(secs) = header("retry-after").and_then(|v| v.parse::<u64>().ok()) {
+        return Some(Duration::from_secs(secs));
+    }
+    if let Some(reset) = header("x-ratelimit-reset").and_then(|v| v.parse::<u64>().ok()) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        return Some(Duration::from_secs(reset.saturating_sub(now) + 1));
+    }
+    Some(Duration::from_secs(1 << attempt))
+}
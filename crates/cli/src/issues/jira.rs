@@ -0,0 +1,194 @@
+//! Jira tracker (REST API v2)
+//!
+//! - `JIRA_EMAIL`, `JIRA_API_TOKEN` — basic authentication
+//! - `[issues.jira] url`, `project` — the site and the project key
+//!
+//! Open issues are found with JQL on the project, the `[issues] label` and
+//! `statusCategory != Done`, 100 per page by `startAt`. Closing comments,
+//! then applies the `done_transition` workflow transition by name.
+
+use anyhow::{bail, Context, Result};
+use revet_core::IssuesConfig;
+use serde_json::{json, Value};
+
+use super::http::Http;
+use super::{marker_of, Issue, NewIssue, Tracker};
+use crate::exit;
+
+/// Issues asked for per search page
+const PAGE_SIZE: u64 = 100;
+
+pub struct JiraTracker {
+    http: Http,
+    url: String,
+    project: String,
+    email: String,
+    token: String,
+    label: String,
+    issue_type: String,
+    done_transition: String,
+}
+
+impl JiraTracker {
+    /// The tracker for `config`, credentials from the environment
+    pub fn from_env(config: &IssuesConfig) -> Result<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let (Some(url), Some(project)) = (&config.jira.url, &config.jira.project) else {
+            return Err(exit::usage(
+                "--tracker jira needs [issues.jira] url and project",
+            ));
+        };
+        let (Some(email), Some(token)) = (var("JIRA_EMAIL"), var("JIRA_API_TOKEN")) else {
+            return Err(exit::environment(
+                "--tracker jira needs JIRA_EMAIL and JIRA_API_TOKEN",
+            ));
+        };
+        Ok(Self {
+            http: Http::new()?,
+            url: url.trim_end_matches('/').to_string(),
+            project: project.clone(),
+            email,
+            token,
+            label: config.label.clone(),
+            issue_type: config.jira.issue_type.clone(),
+            done_transition: config.jira.done_transition.clone(),
+        })
+    }
+
+    /// The issue of a search result; `None` without a marker
+    fn issue_of(&self, item: &Value) -> Option<Issue> {
+        let key = item["key"].as_str()?;
+        let fingerprint = marker_of(item["fields"]["description"].as_str()?)?;
+        Some(Issue {
+            key: key.to_string(),
+            title: item["fields"]["summary"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            fingerprint: fingerprint.to_string(),
+            url: format!("{}/browse/{}", self.url, key),
+        })
+    }
+
+    fn api(&self, path: &str) -> String {
+        format!("{}/rest/api/2/{}", self.url, path)
+    }
+
+    fn request(
+        &self,
+        what: &str,
+        build: impl Fn(&reqwest::blocking::Client) -> reqwest::blocking::RequestBuilder,
+    ) -> Result<reqwest::blocking::Response> {
+        self.http.send(what, |client| {
+            build(client)
+                .basic_auth(&self.email, Some(&self.token))
+                .header("Accept", "application/json")
+        })
+    }
+}
+
+impl Tracker for JiraTracker {
+    fn name(&self) -> &str {
+        "Jira"
+    }
+
+    fn open_issues(&self) -> Result<Vec<Issue>> {
+        let jql = format!(
+            "project = \"{}\" AND labels = \"{}\" AND statusCategory != Done",
+            self.project, self.label
+        );
+        let mut issues = Vec::new();
+        let page_size = PAGE_SIZE.to_string();
+        let mut start_at = 0u64;
+        loop {
+            let start = start_at.to_string();
+            let resp = self.request("search issues", |client| {
+                client.get(self.api("search")).query(&[
+                    ("jql", jql.as_str()),
+                    ("fields", "summary,description"),
+                    ("startAt", start.as_str()),
+                    ("maxResults", page_size.as_str()),
+                ])
+            })?;
+            let page: Value = resp.json().context("search issues: unexpected answer")?;
+            let items = page["issues"].as_array().cloned().unwrap_or_default();
+            issues.extend(items.iter().filter_map(|item| self.issue_of(item)));
+            start_at += items.len() as u64;
+            let total = page["total"].as_u64().unwrap_or_default();
+            if items.is_empty() || start_at >= total {
+                return Ok(issues);
+            }
+        }
+    }
+
+    fn create(&self, issue: &NewIssue) -> Result<Issue> {
+        let body = json!({
+            "fields": {
+                "project": { "key": self.project },
+                "summary": issue.title,
+                "description": issue.body,
+                "issuetype": { "name": self.issue_type },
+                "labels": [self.label],
+            }
+        });
+        let resp = self.request("create issue", |client| {
+            client.post(self.api("issue")).json(&body)
+        })?;
+        let created: Value = resp.json().context("create issue: unexpected answer")?;
+        let key = created["key"]
+            .as_str()
+            .context("create issue: no key in the answer")?;
+        Ok(Issue {
+            key: key.to_string(),
+            title: issue.title.clone(),
+            fingerprint: issue.fingerprint.clone(),
+            url: format!("{}/browse/{}", self.url, key),
+        })
+    }
+
+    fn close(&self, issue: &Issue, comment: &str) -> Result<()> {
+        let what = format!("close issue {}", issue.key);
+        let body = json!({ "body": comment });
+        self.request(&what, |client| {
+            client
+                .post(self.api(&format!("issue/{}/comment", issue.key)))
+                .json(&body)
+        })?;
+
+        let resp = self.request(&what, |client| {
+            client.get(self.api(&format!("issue/{}/transitions", issue.key)))
+        })?;
+        let transitions: Value = resp.json().context("list transitions: unexpected answer")?;
+        let transitions = transitions["transitions"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let Some(id) = transitions
+            .iter()
+            .find(|t| {
+                t["name"]
+                    .as_str()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(&self.done_transition))
+            })
+            .and_then(|t| t["id"].as_str())
+        else {
+            let names: Vec<&str> = transitions
+                .iter()
+                .filter_map(|t| t["name"].as_str())
+                .collect();
+            bail!(
+                "{}: no \"{}\" transition (available: {}); set [issues.jira] done_transition",
+                what,
+                self.done_transition,
+                names.join(", ")
+            );
+        };
+        let body = json!({ "transition": { "id": id } });
+        self.request(&what, |client| {
+            client
+                .post(self.api(&format!("issue/{}/transitions", issue.key)))
+                .json(&body)
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,324 @@
+//! Issue tracker sync (`revet issues sync`)
+//!
+//! Findings that survive `min_age_runs` consecutive full runs on the
+//! default branch get an issue; issues whose finding is gone from the latest
+//! run get a comment and are closed. The join key is a fingerprint marker in
+//! the issue body, so a re-run finds the issues it created and changes
+//! nothing:
+//!
+//! ```text
+//! revet-fingerprint: 3f9a0c1d2b7e8a64
+//! ```
+//!
+//! The fingerprint hashes the file, rule and redacted message of a finding,
+//! the same line-independent key the baseline matches on, so an edit above
+//! a finding doesn't close its issue and open another.
+//!
+//! Trackers sit behind [`Tracker`]: [`github::GithubTracker`] and
+//! [`jira::JiraTracker`]. [`sync`] reports what it did per issue and carries
+//! on past a failed call, so one rejected request doesn't leave the rest
+//! undone.
+
+pub mod github;
+mod http;
+pub mod jira;
+
+use anyhow::Result;
+use revet_core::analyzer::registry::prefix_of;
+use revet_core::suppress::matches_finding;
+use revet_core::{Finding, IssuesConfig};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+use crate::run_log::{RunFinding, RunLog};
+
+/// Start of the line in an issue body holding the fingerprint
+pub const MARKER: &str = "revet-fingerprint:";
+
+/// An issue revet manages, found by its label and fingerprint marker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    /// `#123` on GitHub, `SEC-45` on Jira
+    pub key: String,
+    pub title: String,
+    pub fingerprint: String,
+    pub url: String,
+}
+
+/// An issue to create for a persistent finding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewIssue {
+    pub fingerprint: String,
+    pub title: String,
+    pub body: String,
+}
+
+/// An issue tracker
+pub trait Tracker {
+    /// Name shown in the report (`GitHub`, `Jira`)
+    fn name(&self) -> &str;
+
+    /// Every open issue carrying a fingerprint marker, all pages of them
+    fn open_issues(&self) -> Result<Vec<Issue>>;
+
+    /// Create `issue`; its body already ends with the marker
+    fn create(&self, issue: &NewIssue) -> Result<Issue>;
+
+    /// Comment `comment` on `issue`, then close (or resolve) it
+    fn close(&self, issue: &Issue, comment: &str) -> Result<()>;
+}
+
+/// Fingerprint of a finding: file, rule (else prefix) and redacted message
+pub fn fingerprint(finding: &RunFinding) -> String {
+    let rule = finding
+        .rule
+        .as_deref()
+        .unwrap_or_else(|| prefix_of(&finding.id));
+    let mut hasher = Sha256::new();
+    for part in [finding.file.as_str(), rule, finding.message.as_str()] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// The fingerprint in an issue body, if it has a marker
+pub fn marker_of(body: &str) -> Option<&str> {
+    body.lines().find_map(|line| {
+        let rest = line.trim().trim_start_matches("<!--").trim();
+        let fingerprint = rest.strip_prefix(MARKER)?.trim().trim_end_matches("-->");
+        let fingerprint = fingerprint.trim();
+        (!fingerprint.is_empty() && fingerprint.bytes().all(|b| b.is_ascii_hexdigit()))
+            .then_some(fingerprint)
+    })
+}
+
+/// Which findings are tracked
+#[derive(Debug, Clone)]
+pub struct Selection {
+    /// Least severe finding tracked
+    pub min_severity: String,
+    /// Consecutive runs a finding must be in
+    pub min_age_runs: usize,
+    /// Rule tokens; empty for all
+    pub rules: Vec<String>,
+}
+
+impl Selection {
+    pub fn from_config(config: &IssuesConfig) -> Self {
+        Self {
+            min_severity: config.min_severity.clone(),
+            min_age_runs: config.min_age_runs,
+            rules: config.rules.clone(),
+        }
+    }
+
+    fn selects(&self, finding: &RunFinding) -> bool {
+        if finding.suppressed || rank(&finding.severity) < rank(&self.min_severity) {
+            return false;
+        }
+        if self.rules.is_empty() {
+            return true;
+        }
+        let probe = Finding {
+            id: finding.id.clone(),
+            rule: finding.rule.clone(),
+            ..Default::default()
+        };
+        matches_finding(&probe, &self.rules)
+    }
+}
+
+fn rank(severity: &str) -> u8 {
+    match severity {
+        "error" => 3,
+        "warning" => 2,
+        "info" => 1,
+        _ => 0,
+    }
+}
+
+/// A finding that survived enough runs to be tracked
+#[derive(Debug, Clone)]
+pub struct Persistent<'a> {
+    pub fingerprint: String,
+    /// The finding as the latest run reported it
+    pub finding: &'a RunFinding,
+    /// Consecutive runs, latest first, the finding is in
+    pub runs: usize,
+}
+
+/// Findings of the latest run of `history` (newest first) that
+/// `selection` picks and that every one of the `min_age_runs` latest runs
+/// reported
+pub fn persistent<'a>(history: &'a [RunLog], selection: &Selection) -> Vec<Persistent<'a>> {
+    let Some(latest) = history.first() else {
+        return Vec::new();
+    };
+    if history.len() < selection.min_age_runs {
+        return Vec::new();
+    }
+    let per_run: Vec<HashSet<String>> = history.iter().map(reported).collect();
+    let mut seen = HashSet::new();
+    latest
+        .findings
+        .iter()
+        .filter(|f| selection.selects(f))
+        .filter_map(|finding| {
+            let fingerprint = fingerprint(finding);
+            if !seen.insert(fingerprint.clone()) {
+                return None;
+            }
+            let runs = per_run
+                .iter()
+                .take_while(|run| run.contains(&fingerprint))
+                .count();
+            (runs >= selection.min_age_runs).then_some(Persistent {
+                fingerprint,
+                finding,
+                runs,
+            })
+        })
+        .collect()
+}
+
+/// Fingerprints of the findings `log` reported (not suppressed)
+pub fn reported(log: &RunLog) -> HashSet<String> {
+    log.findings
+        .iter()
+        .filter(|f| !f.suppressed)
+        .map(fingerprint)
+        .collect()
+}
+
+/// The issue for `persistent`
+pub fn new_issue(persistent: &Persistent) -> NewIssue {
+    let f = persistent.finding;
+    let title = format!("[revet] {}: {}", prefix_of(&f.id), headline(&f.message));
+    let mut body = format!(
+        "**{}** `{}` in `{}:{}`\n\n{}\n",
+        f.severity, f.id, f.file, f.line, f.message
+    );
+    if let Some(rule) = &f.rule {
+        body.push_str(&format!("\nRule: `{}`\n", rule));
+    }
+    if let Some(details) = f.details.as_ref().filter(|d| !d.is_empty()) {
+        body.push_str(&format!("\n```\n{}\n```\n", details.lines().join("\n")));
+    }
+    body.push_str(&format!(
+        "\nReported by revet in the last {} full runs. The issue is closed \
+         automatically once the finding is gone.\n\n{} {}\n",
+        persistent.runs, MARKER, persistent.fingerprint
+    ));
+    NewIssue {
+        fingerprint: persistent.fingerprint.clone(),
+        title,
+        body,
+    }
+}
+
+/// First line of `message`, cut to a title's length
+fn headline(message: &str) -> String {
+    const MAX: usize = 100;
+    let line = message.lines().next().unwrap_or_default().trim();
+    match line.char_indices().nth(MAX) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+/// What a sync does
+#[derive(Debug, Default)]
+pub struct Plan {
+    pub create: Vec<NewIssue>,
+    /// Open issues whose finding the latest run no longer reports
+    pub close: Vec<Issue>,
+}
+
+/// Issues to create for the persistent findings without one, and issues to
+/// close whose fingerprint `latest` (the latest run's) no longer has
+///
+/// Of several open issues with one fingerprint (a copy made by hand, say)
+/// only the first is considered.
+pub fn plan(persistent: &[Persistent], latest: &HashSet<String>, mut open: Vec<Issue>) -> Plan {
+    let mut seen = HashSet::new();
+    open.retain(|issue| seen.insert(issue.fingerprint.clone()));
+    let tracked: HashSet<&str> = open.iter().map(|i| i.fingerprint.as_str()).collect();
+    let create = persistent
+        .iter()
+        .filter(|p| !tracked.contains(p.fingerprint.as_str()))
+        .map(new_issue)
+        .collect();
+    let close = open
+        .into_iter()
+        .filter(|issue| !latest.contains(&issue.fingerprint))
+        .collect();
+    Plan { create, close }
+}
+
+/// What a sync did: the issues created and closed, and the calls that
+/// failed
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub created: Vec<Issue>,
+    pub closed: Vec<Issue>,
+    pub failures: Vec<String>,
+}
+
+/// A tracker that lists issues through another but creates and closes
+/// nothing (`--dry-run`): the report shows what a sync would do
+pub struct DryRun<'a>(pub &'a dyn Tracker);
+
+impl Tracker for DryRun<'_> {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    fn open_issues(&self) -> Result<Vec<Issue>> {
+        self.0.open_issues()
+    }
+
+    fn create(&self, issue: &NewIssue) -> Result<Issue> {
+        Ok(Issue {
+            key: String::new(),
+            title: issue.title.clone(),
+            fingerprint: issue.fingerprint.clone(),
+            url: String::new(),
+        })
+    }
+
+    fn close(&self, _issue: &Issue, _comment: &str) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The comment an issue is closed with
+pub const CLOSE_COMMENT: &str =
+    "The finding is no longer reported by revet on the default branch. Closing.";
+
+/// Carry out `plan` on `tracker`, one call per issue
+pub fn sync(tracker: &dyn Tracker, plan: Plan) -> SyncReport {
+    let mut report = SyncReport::default();
+    for issue in &plan.create {
+        match tracker.create(issue) {
+            Ok(created) => report.created.push(created),
+            Err(e) => report
+                .failures
+                .push(format!("create \"{}\": {:#}", issue.title, e)),
+        }
+    }
+    for issue in plan.close {
+        match tracker.close(&issue, CLOSE_COMMENT) {
+            Ok(()) => report.closed.push(issue),
+            Err(e) => report
+                .failures
+                .push(format!("close {}: {:#}", issue.key, e)),
+        }
+    }
+    report
+}
@@ -2,13 +2,19 @@
 
 pub mod ai;
 pub mod commands;
+pub mod exit;
+pub mod issues;
 pub mod output;
+pub mod profile;
 #[allow(dead_code)]
 pub mod progress;
+pub mod remote_cache;
 pub mod run_log;
+pub mod webhook;
 
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use revet_core::{MutationJournal, StoragePaths};
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "revet")]
@@ -18,6 +24,11 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
 
+    /// Analysis profile: quick, standard (default), deep, or a [profiles.<name>] section.
+    /// Explicit flags override the profile's settings.
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
     /// Diff base (default: main)
     #[arg(long, global = true)]
     pub diff: Option<String>,
@@ -26,17 +37,43 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub full: bool,
 
+    /// Review only the files changed since REV, committed or not, keeping
+    /// their findings on unchanged lines too; graph analyzers also see the
+    /// files those import or are imported by
+    #[arg(long, global = true, value_name = "REV", conflicts_with_all = ["full", "diff"])]
+    pub changed_since: Option<String>,
+
+    /// Analyze files a sparse checkout leaves out as well, when present on
+    /// disk (they are skipped by default)
+    #[arg(long, global = true)]
+    pub include_sparse_excluded: bool,
+
     /// Enable LLM reasoning (opt-in)
     #[arg(long, global = true)]
     pub ai: bool,
 
-    /// Specific domain modules to run
+    /// Specific domain modules to run (`module` or `module:rule`)
     #[arg(long, value_delimiter = ',', global = true)]
     pub module: Option<Vec<String>>,
 
-    /// Output format
+    /// Output format, with `=PATH` to write the report to a file instead of
+    /// stdout. Repeat for several reports from one run, e.g. `--format
+    /// sarif=revet.sarif --format markdown=comment.md --format terminal`
+    #[arg(long, global = true, value_name = "FORMAT[=PATH]", value_parser = FormatArg::parse)]
+    pub format: Vec<FormatArg>,
+
+    /// Write the JSON, SARIF or markdown report here instead of stdout
+    #[arg(long, global = true, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    /// In diff-based runs, report complexity only where a change makes a
+    /// function worse (same as `[analyzers.complexity] mode = "delta"`)
+    #[arg(long, global = true)]
+    pub complexity_delta: bool,
+
+    /// Report only findings in production, test or script code
     #[arg(long, value_enum, global = true)]
-    pub format: Option<OutputFormat>,
+    pub only: Option<OnlyKind>,
 
     /// Severity threshold for non-zero exit: error, warning, info, never
     #[arg(long, global = true)]
@@ -47,31 +84,140 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub gate: Option<String>,
 
+    /// Print why revet exited as one line on stderr, e.g.
+    /// `exit=1 reason=findings_exceed_threshold count=7 threshold=warning`
+    #[arg(long, global = true)]
+    pub print_exit_reason: bool,
+
+    /// List the findings a `[[policy]]` matched instead of the normal output
+    #[arg(long, global = true, value_name = "NAME")]
+    pub explain_policy: Option<String>,
+
     /// Apply automatic fixes
     #[arg(long, global = true)]
     pub fix: bool,
 
+    /// Don't record changes to files (`--fix`, baseline writes) in the audit
+    /// log; the next entry written notes the gap
+    #[arg(long, global = true)]
+    pub no_audit: bool,
+
     /// Post findings as inline GitHub PR review comments via GitHub API.
     /// Reads GITHUB_TOKEN, GITHUB_REPOSITORY, GITHUB_PR_NUMBER, GITHUB_SHA
     /// from the environment (all set automatically by GitHub Actions).
     #[arg(long, global = true)]
     pub post_comment: bool,
 
+    /// Append a markdown report to the GitHub Actions job summary
+    /// (the file named by $GITHUB_STEP_SUMMARY)
+    #[arg(long, global = true)]
+    pub github_job_summary: bool,
+
     /// Ignore baseline — show all findings
     #[arg(long, global = true)]
     pub no_baseline: bool,
 
+    /// Don't apply `revet ignore` entries (they never apply in CI anyway)
+    #[arg(long, global = true)]
+    pub no_session_ignores: bool,
+
+    /// Report every module's findings, ignoring `rollout_percent` sampling
+    #[arg(long, global = true)]
+    pub no_rollout_sampling: bool,
+
+    /// Parse every file instead of reusing the per-file graph cache; the
+    /// cache is neither read nor written
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
+    /// Print the baseline delta event a command would send to `[reporting]
+    /// webhook_url`, and its signature, instead of sending it
+    #[arg(long, global = true)]
+    pub webhook_dry_run: bool,
+
     /// Include suppressed findings in output (marked [suppressed])
     #[arg(long, global = true)]
     pub show_suppressed: bool,
 
+    /// Print each finding's details (full query text, every location,
+    /// version tables) under its headline in terminal output
+    #[arg(long, global = true)]
+    pub verbose: bool,
+
     /// Max cost for LLM calls in USD
     #[arg(long, global = true)]
     pub max_cost: Option<f64>,
 
-    /// Print per-analyzer timing breakdown after analysis
+    /// With --ai: report the requests and cost the run would make, without calling the API
+    #[arg(long, global = true)]
+    pub estimate_only: bool,
+
+    /// With --ai: send only findings the baseline doesn't record
+    #[arg(long, global = true)]
+    pub ai_only_new: bool,
+
+    /// With --ai: least severe finding to send
+    #[arg(long, value_enum, global = true, value_name = "SEVERITY")]
+    pub ai_min_severity: Option<AiMinSeverity>,
+
+    /// Stream each directory's findings to stderr as soon as every per-file
+    /// analyzer is done with it (`github` annotations or `ndjson` lines).
+    /// The normal output, summary and exit code still wait for the review
+    #[arg(long, value_enum, global = true)]
+    pub progressive: Option<ProgressiveFormat>,
+
+    /// Print per-analyzer timing breakdown after analysis: `summary`
+    /// (default) or `full`, adding each analyzer's slowest files
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "summary"
+    )]
+    pub timings: Option<TimingsDetail>,
+
+    /// Print per-rule finding counts and how many code suggestions were
+    /// checked and demoted to prose after analysis
+    #[arg(long, global = true)]
+    pub show_rule_stats: bool,
+
+    /// Finish within this time (`300s`, `5m`): phases whose estimate from
+    /// earlier runs no longer fits are skipped and listed in the summary
+    #[arg(long, global = true, value_name = "DURATION", value_parser = revet_core::schedule::parse_budget)]
+    pub time_budget: Option<std::time::Duration>,
+
+    /// Report which files each analyzer covered and why others were skipped:
+    /// `summary` (default) or `full` per-file detail
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "summary"
+    )]
+    pub coverage_report: Option<CoverageDetail>,
+
+    /// Print raw secret values instead of masking them (requires --confirm-no-redact)
+    #[arg(long, global = true, requires = "confirm_no_redact")]
+    pub no_redact: bool,
+
+    /// Confirm that raw secret values may be written to output and logs
     #[arg(long, global = true)]
-    pub timings: bool,
+    pub confirm_no_redact: bool,
+
+    /// Review one project of a monorepo (a directory with a manifest, or a
+    /// `[[project]]`): its files and those of the projects it depends on
+    /// are scanned in full, and only findings in its own files are reported
+    #[arg(long, global = true, value_name = "DIR")]
+    pub project: Option<PathBuf>,
+
+    /// With --project: exit 5 without reviewing when the project's inputs
+    /// no longer hash to this digest (see `revet hash-inputs`)
+    #[arg(long, global = true, value_name = "DIGEST", requires = "project")]
+    pub assert_hash: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -96,22 +242,83 @@ pub enum Commands {
     Review {
         /// Path to repository (default: current directory)
         path: Option<PathBuf>,
+
+        /// Review the file content read from stdin instead of the
+        /// repository's files, as the file named by --path
+        #[arg(long, requires = "stdin_path")]
+        stdin: bool,
+
+        /// Repository path the stdin content is reviewed as (e.g.
+        /// src/foo.ts); findings, baselines and suppressions use it
+        #[arg(long = "path", value_name = "FILE", requires = "stdin")]
+        stdin_path: Option<PathBuf>,
+    },
+
+    /// Review every repository listed in a revet-workspace.toml in parallel
+    Multi {
+        /// Workspace file, or a directory containing revet-workspace.toml
+        /// (default: current directory)
+        workspace: Option<PathBuf>,
+
+        /// Repositories reviewed at once (overrides `jobs` in the workspace file)
+        #[arg(long, short = 'j')]
+        jobs: Option<usize>,
+    },
+
+    /// Review for CI: in GitHub Actions, picks the annotation style for the
+    /// event (problem matcher on fork PRs), groups output per file and
+    /// writes the job summary
+    Ci {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Print the problem matcher JSON for `--format github-matcher` and exit
+        #[arg(long)]
+        emit_matcher: bool,
+
+        /// Upload the SARIF report written with `--output` (every chunk, in
+        /// order) to GitHub code scanning
+        #[arg(long)]
+        upload_sarif: bool,
+    },
+
+    /// Review with findings from other tools' reports merged in, so
+    /// suppression, the baseline, policies and the gate cover them too
+    Import {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+
+        #[command(flatten)]
+        reports: ImportArgs,
     },
 
     /// Show findings only on changed lines
     Diff {
-        /// Base branch or commit to diff against
-        base: String,
+        #[command(flatten)]
+        args: DiffArgs,
     },
 
     /// Snapshot current findings as a baseline
+    #[command(args_conflicts_with_subcommands = true)]
     Baseline {
+        #[command(subcommand)]
+        command: Option<BaselineCommand>,
+
         /// Path to repository (default: current directory)
         path: Option<PathBuf>,
 
         /// Remove the existing baseline
         #[arg(long)]
         clear: bool,
+
+        /// Baseline only the findings of the change checked out (HEAD
+        /// against its parent), kept under its `Change-Id` so new
+        /// patchsets of the change still match
+        #[arg(long, conflicts_with_all = ["clear", "from"])]
+        change: bool,
+
+        #[command(flatten)]
+        reports: ImportArgs,
     },
 
     /// Watch for file changes and analyze continuously
@@ -142,8 +349,33 @@ pub enum Commands {
         last: Option<usize>,
     },
 
+    /// Baseline entries added, fixed and expired over the full runs on a
+    /// branch (--format terminal | json)
+    Trend {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+
+        /// One row per owning team in `CODEOWNERS`
+        #[arg(long)]
+        baseline_by_owner: bool,
+
+        /// Runs of the last N days
+        #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u64).range(1..))]
+        days: u64,
+
+        /// Branch whose full runs count (default: [general] diff_base)
+        #[arg(long)]
+        branch: Option<String>,
+    },
+
     /// Validate .revet.toml configuration
-    ConfigCheck,
+    #[command(alias = "validate-config")]
+    ConfigCheck {
+        /// Also list every module's sub-rules, custom ones marked, and where
+        /// suppression comments apply
+        #[arg(long)]
+        list_rules: bool,
+    },
 
     /// Generate an HTML quality report from run history
     Report {
@@ -155,12 +387,584 @@ pub enum Commands {
         #[arg(long, short = 'n')]
         last: Option<usize>,
     },
+
+    /// Print one file's findings and quick fixes as LSP diagnostics and
+    /// code actions (--format lsp-json)
+    Fixes {
+        /// File to analyze
+        #[arg(long)]
+        file: PathBuf,
+
+        /// Read the file's current (unsaved) contents from stdin
+        #[arg(long)]
+        stdin: bool,
+    },
+
+    /// Generate a software bill of materials from lockfiles
+    /// (--format cyclonedx | spdx-json, default cyclonedx)
+    Sbom {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Output file (default: stdout)
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+
+        /// Offline advisory file (overrides [modules] dependency_advisories)
+        #[arg(long)]
+        advisories: Option<PathBuf>,
+    },
+
+    /// Summarize what blocks upgrading a runtime: removed, changed and
+    /// deprecated APIs per module (--format terminal | json)
+    UpgradeReport {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Target version, e.g. `python=3.13` or `node=22`; repeatable.
+        /// Overrides `[upgrade]` and limits the report to these runtimes
+        #[arg(long = "target", value_name = "RUNTIME=VERSION")]
+        targets: Vec<String>,
+    },
+
+    /// Suggest thresholds for the threshold-based analyzers from the
+    /// repository's own metric distributions; nothing is applied
+    Calibrate {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Flag at most the items above this percentile of each metric
+        /// (overrides [calibration] percentile)
+        #[arg(long, conflicts_with = "max_findings")]
+        percentile: Option<u8>,
+
+        /// Leave at most this many findings per rule (overrides
+        /// [calibration] max_findings)
+        #[arg(long)]
+        max_findings: Option<usize>,
+
+        /// Write the suggested [calibration] section to this file instead
+        /// of stdout
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+    },
+
+    /// Print the digest of everything a `--project`'s findings depend on,
+    /// for Nx and Turborepo task caching (--format json lists the inputs)
+    HashInputs {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+    },
+
+    /// Manage the graph store's snapshots (`graph.db` in the state directory)
+    Snapshot {
+        #[command(subcommand)]
+        command: SnapshotCommand,
+    },
+
+    /// Track findings that persist on the default branch as issues in
+    /// GitHub or Jira
+    Issues {
+        #[command(subcommand)]
+        command: IssuesCommand,
+    },
+
+    /// Show or verify the audit log of the changes revet made to files
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommand,
+    },
+
+    /// Hide findings on this machine for a while, without touching the
+    /// baseline, the source or the config. Never applied in CI
+    Ignore {
+        #[command(subcommand)]
+        command: IgnoreCommand,
+    },
+
+    /// Keep revet resident for a repository so `review` and `diff` there
+    /// skip startup and reuse parsed files (Unix only)
+    Daemon {
+        #[command(subcommand)]
+        command: DaemonCommand,
+    },
+
+    /// Manage where revet keeps caches, run logs and the baseline
+    Storage {
+        #[command(subcommand)]
+        command: StorageCommand,
+    },
+
+    /// Bundle a finding into a self-contained case file and re-run its
+    /// analyzer on one, for debugging false positives and negatives
+    Debug {
+        #[command(subcommand)]
+        command: DebugCommand,
+    },
+
+    /// Serve the last run's findings, summary and coverage and the cached
+    /// graph over a read-only HTTP API (no analysis is run)
+    #[cfg(feature = "api")]
+    Api {
+        /// Repository, workspace file, or directory containing
+        /// revet-workspace.toml (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Address to listen on
+        #[arg(long, default_value = commands::api::DEFAULT_BIND)]
+        bind: std::net::SocketAddr,
+
+        /// Allow --bind on a non-loopback address (the API has no authentication)
+        #[arg(long)]
+        allow_remote: bool,
+    },
+}
+
+/// External reports for `revet import` and `revet baseline`
+#[derive(Args, Debug, Clone, Default)]
+pub struct ImportArgs {
+    /// Report to import: its format (sarif, gitleaks, semgrep) and file.
+    /// Repeat for several reports
+    #[arg(
+        long = "from",
+        num_args = 2,
+        value_names = ["FORMAT", "FILE"],
+        action = ArgAction::Append
+    )]
+    pub from: Vec<String>,
+
+    /// Leading path of the reports' file paths that stands for the
+    /// repository root (e.g. `/github/workspace`)
+    #[arg(long, value_name = "PREFIX")]
+    pub path_prefix_strip: Option<String>,
+}
+
+/// What `revet diff` compares
+#[derive(Args, Debug, Clone, Default)]
+pub struct DiffArgs {
+    /// Base branch or commit to diff against; with `--stack`, the commit
+    /// the stack is based on
+    pub base: Option<String>,
+
+    /// Find the base in the review system: `gerrit` diffs the change under
+    /// review against its parent patchset
+    #[arg(long, value_enum, value_name = "SYSTEM")]
+    pub diff_base_detect: Option<BaseDetect>,
+
+    /// Diff against this commit, the change's parent patchset
+    #[arg(long, value_name = "SHA", conflicts_with = "base")]
+    pub parent_change: Option<String>,
+
+    /// Review each commit between the base and HEAD on its own, against its
+    /// first parent, and report the findings per change
+    #[arg(long)]
+    pub stack: bool,
+
+    /// Diff against the commit HEAD branched off the base at, so changes
+    /// the base gained since are left out (`git diff base...HEAD`)
+    #[arg(long, conflicts_with_all = ["diff_base_detect", "parent_change", "stack"])]
+    pub merge_base: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum BaseDetect {
+    /// The parent of the change (`refs/changes/..`, `GERRIT_*` and `ZUUL_*`
+    /// job variables), so a change of a stack is diffed against the one
+    /// below it
+    Gerrit,
+}
+
+#[derive(Subcommand)]
+pub enum BaselineCommand {
+    /// Walk through baseline entries rule by rule: keep them, expire them,
+    /// give them an expiry date or turn them into inline suppressions
+    Review {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Print how many entries still match, no longer match or point at
+        /// deleted files, without prompting (`--format json` for CI)
+        #[arg(long)]
+        report: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SnapshotCommand {
+    /// Delete the snapshots the retention policy does not keep and rewrite
+    /// over-long delta chains as full snapshots
+    Gc {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Keep the N newest snapshots (overrides `[cache] keep_last`)
+        #[arg(long)]
+        keep_last: Option<usize>,
+
+        /// Keep snapshots from the last N days (overrides `[cache] keep_days`)
+        #[arg(long)]
+        keep_days: Option<u64>,
+    },
+
+    /// Release notes between two snapshots: added, removed, moved and
+    /// renamed symbols, signature changes and structural churn
+    Changelog {
+        /// Older snapshot name
+        old: String,
+
+        /// Newer snapshot name
+        new: String,
+
+        /// Path to repository (default: current directory)
+        #[arg(long)]
+        path: Option<PathBuf>,
+
+        /// Which symbols to report
+        #[arg(long, value_enum, default_value = "all")]
+        filter: ChangelogFilter,
+
+        /// Report files whose node count changed by at least N
+        #[arg(long, default_value_t = revet_core::diff::changelog::DEFAULT_CHURN_THRESHOLD)]
+        churn_threshold: usize,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AuditCommand {
+    /// Check the log's hash chain; exits 1 when an entry was modified,
+    /// removed or cut off
+    Verify {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+    },
+    /// Print the log's entries (--format json for the raw entries)
+    Show {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Only entries from this date on
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        since: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum IssuesCommand {
+    /// Create issues for findings that survived the latest full runs on the
+    /// default branch, and close the issues whose finding is gone
+    Sync {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Where the issues live
+        #[arg(long, value_enum)]
+        tracker: Tracker,
+
+        /// Least severe finding tracked (overrides [issues] min_severity)
+        #[arg(long, value_parser = ["error", "warning", "info"])]
+        min_severity: Option<String>,
+
+        /// Consecutive full runs a finding must be in (overrides [issues]
+        /// min_age_runs)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        min_age_runs: Option<u64>,
+
+        /// Only findings of this rule: an ID prefix, module or
+        /// `module:rule`; repeatable (overrides [issues] rules)
+        #[arg(long = "rule")]
+        rules: Vec<String>,
+
+        /// Branch whose runs count (overrides [issues] branch, default the
+        /// diff base)
+        #[arg(long)]
+        branch: Option<String>,
+
+        /// Print the issues that would be created and closed, and change
+        /// nothing
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Tracker {
+    Github,
+    Jira,
+}
+
+#[derive(Subcommand)]
+pub enum IgnoreCommand {
+    /// Hide a finding of the last run by ID, or every finding of a rule
+    /// and/or under a path
+    Add {
+        /// Finding ID, as printed by the last `revet review` (e.g. SEC-004)
+        #[arg(required_unless_present_any = ["rule", "path"], conflicts_with_all = ["rule", "path"])]
+        id: Option<String>,
+
+        /// Rule to hide: an ID prefix, module or `module:rule`, as in a
+        /// `revet-ignore` comment
+        #[arg(long)]
+        rule: Option<String>,
+
+        /// Glob of repository paths to hide findings in, e.g. `src/wip/**`
+        #[arg(long, value_name = "GLOB")]
+        path: Option<String>,
+
+        /// How long the entry lasts (`30m`, `8h`)
+        #[arg(long, default_value = "24h", value_parser = revet_core::session_ignore::parse_ttl)]
+        ttl: std::time::Duration,
+
+        /// Path to repository (default: current directory)
+        #[arg(long, value_name = "DIR")]
+        repo: Option<PathBuf>,
+    },
+    /// List the entries still in effect and the time they have left
+    List {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+    },
+    /// Remove every entry
+    Clear {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DaemonCommand {
+    /// Start a daemon for the repository in the background
+    Start {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Exit after this long without requests (`30m`, `2h`)
+        #[arg(long, default_value = commands::daemon::DEFAULT_IDLE_TIMEOUT, value_parser = revet_core::schedule::parse_budget)]
+        idle_timeout: std::time::Duration,
+    },
+    /// Serve the repository in the foreground (what `start` runs)
+    #[command(hide = true)]
+    Run {
+        path: Option<PathBuf>,
+
+        #[arg(long, default_value = commands::daemon::DEFAULT_IDLE_TIMEOUT, value_parser = revet_core::schedule::parse_budget)]
+        idle_timeout: std::time::Duration,
+    },
+    /// Run the requests the daemon hands over on stdin (what `run` starts)
+    #[command(hide = true)]
+    Worker {
+        path: Option<PathBuf>,
+
+        /// Colour output, as the client would
+        #[arg(long)]
+        color: bool,
+    },
+    /// Show whether a daemon serves the repository, and what it holds
+    Status {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+    },
+    /// Stop the repository's daemon
+    Stop {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum StorageCommand {
+    /// Move state from `.revet-cache/` in the repository to the configured
+    /// data directory (`REVET_DATA_DIR` or `[storage] data_dir`) and baseline
+    /// path (`[baseline] path`)
+    Migrate {
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DebugCommand {
+    /// Write a finding of a recorded run, the file it is in, the config its
+    /// analyzer reads and (for graph analyzers) the file's graph
+    /// neighbourhood to a `.revetcase` archive
+    Capture {
+        /// Finding ID, as printed by `revet review` (e.g. SEC-004)
+        #[arg(long)]
+        finding: String,
+
+        /// Case file to write (default: <FINDING>.revetcase)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Path to repository (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Run the finding belongs to (default: the latest; see `revet log`)
+        #[arg(long)]
+        run: Option<String>,
+
+        /// Blank the lines away from the finding and mask secrets on the
+        /// rest (required when the file contains secrets)
+        #[arg(long)]
+        redact: bool,
+
+        /// Lines kept on each side of the finding with --redact
+        #[arg(long, default_value_t = revet_core::case::DEFAULT_CONTEXT)]
+        context: usize,
+
+        /// Accept the redacted content without asking
+        #[arg(long, short)]
+        yes: bool,
+    },
+
+    /// Re-run a case's analyzer alone on its bundled file and report
+    /// whether the finding reproduces (exit code 1 when it does not)
+    Replay {
+        /// Case file written by `revet debug capture`
+        case: PathBuf,
+
+        /// Print the decisions the analyzer made along the way
+        #[arg(long)]
+        trace: bool,
+    },
+}
+
+/// Symbols covered by `revet snapshot changelog`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ChangelogFilter {
+    /// Every top-level function, class and variable
+    All,
+    /// Exported symbols only
+    PublicOnly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CoverageDetail {
+    /// Counts per skip reason
+    Summary,
+    /// Every file with the analyzers that processed it
+    Full,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TimingsDetail {
+    /// Time and findings per analyzer
+    Summary,
+    /// Also the slowest files of each analyzer, and peak allocation in
+    /// `alloc-stats` builds
+    Full,
+}
+
+/// Code kind selected by `--only`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OnlyKind {
+    Production,
+    Test,
+    Script,
+}
+
+impl From<OnlyKind> for revet_core::CodeKind {
+    fn from(kind: OnlyKind) -> Self {
+        match kind {
+            OnlyKind::Production => Self::Production,
+            OnlyKind::Test => Self::Test,
+            OnlyKind::Script => Self::Script,
+        }
+    }
+}
+
+/// Severity selected by `--ai-min-severity`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum AiMinSeverity {
+    Warning,
+    Error,
+}
+
+impl AiMinSeverity {
+    /// Value of `[ai.prioritization] min_severity`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ProgressiveFormat {
+    /// Workflow-command annotations, one `::group::` per partition
+    Github,
+    /// One JSON event per line
+    Ndjson,
+}
+
+impl Cli {
+    /// The journal recording what this command changes in `repo_root`
+    pub fn journal(&self, repo_root: &Path, paths: &StoragePaths) -> MutationJournal {
+        commands::journal(repo_root, paths, self.no_audit)
+    }
+
+    /// The `--format` of commands that print a single report; several, or
+    /// one with a path, is a usage error
+    pub fn single_format(&self) -> anyhow::Result<Option<OutputFormat>> {
+        match self.format.as_slice() {
+            [] => Ok(None),
+            [FormatArg {
+                format,
+                output: None,
+            }] => Ok(Some(*format)),
+            _ => Err(exit::usage(
+                "this command prints one report: pass a single --format without =PATH",
+            )),
+        }
+    }
+}
+
+/// One `--format` value: `json`, or `json=report.json` for a file
+#[derive(Debug, Clone)]
+pub struct FormatArg {
+    pub format: OutputFormat,
+    pub output: Option<PathBuf>,
+}
+
+impl FormatArg {
+    fn parse(value: &str) -> Result<Self, String> {
+        let (name, output) = match value.split_once('=') {
+            Some((_, "")) => return Err(format!("{:?} names no file after `=`", value)),
+            Some((name, path)) => (name, Some(PathBuf::from(path))),
+            None => (value, None),
+        };
+        let format = OutputFormat::from_str(name, true).map_err(|_| {
+            let names: Vec<String> = OutputFormat::value_variants()
+                .iter()
+                .filter_map(|v| v.to_possible_value())
+                .map(|v| v.get_name().to_string())
+                .collect();
+            format!("unknown format {:?} (one of: {})", name, names.join(", "))
+        })?;
+        Ok(Self { format, output })
+    }
 }
 
-#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub enum OutputFormat {
     Terminal,
     Json,
     Sarif,
     Github,
+    /// `file:line:col: severity ID: message` lines for the problem matcher
+    /// printed by `revet ci --emit-matcher`
+    GithubMatcher,
+    /// CycloneDX 1.5 JSON (`revet sbom` only)
+    Cyclonedx,
+    /// SPDX 2.3 JSON (`revet sbom` only)
+    SpdxJson,
+    /// LSP diagnostics and code actions (`revet fixes` only)
+    LspJson,
+    /// Markdown report, as in the job summary (release notes for `revet
+    /// snapshot changelog`)
+    Markdown,
+    /// Checkstyle XML, e.g. for the Jenkins warnings-ng plugin
+    Checkstyle,
+    /// JUnit XML, findings as failed testcases for CI test-report views
+    Junit,
 }
@@ -2,39 +2,135 @@
 
 use anyhow::Result;
 use clap::Parser;
-use revet_cli::{commands, Cli, Commands};
+use revet_cli::exit::{ErrorKind, Outcome};
+use revet_cli::{
+    commands, AuditCommand, BaselineCommand, Cli, Commands, DaemonCommand, DebugCommand,
+    IgnoreCommand, IssuesCommand, SnapshotCommand, StorageCommand,
+};
 
-fn main() -> Result<()> {
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOC: revet_core::alloc_stats::CountingAllocator =
+    revet_core::alloc_stats::CountingAllocator;
+
+/// Run the command and exit with the code of its outcome; see
+/// [`revet_cli::exit`] for the contract. `clap` exits 2 on bad flags.
+fn main() {
     let cli = Cli::parse();
 
+    // `watch` handles Ctrl-C itself to stop cleanly
+    if !matches!(cli.command, Some(Commands::Watch { .. })) {
+        let print_reason = cli.print_exit_reason;
+        let _ = ctrlc::set_handler(move || {
+            if print_reason {
+                eprintln!("{}", ErrorKind::Interrupted.reason());
+            }
+            std::process::exit(ErrorKind::Interrupted.code());
+        });
+    }
+
+    // A daemon serving the repository runs `review` and `diff` instead
+    let (code, reason) = commands::daemon::proxy(&cli).unwrap_or_else(|| execute(&cli));
+    if cli.print_exit_reason {
+        eprintln!("{}", reason);
+    }
+    std::process::exit(code);
+}
+
+/// Run the command: its exit code and `--print-exit-reason` line
+fn execute(cli: &Cli) -> (i32, String) {
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| run(cli))) {
+        Ok(Ok(outcome)) => (outcome.code(), outcome.reason()),
+        Ok(Err(err)) => {
+            eprintln!("Error: {:?}", err);
+            let kind = ErrorKind::of(&err);
+            (kind.code(), kind.reason())
+        }
+        // The panic hook already printed the message
+        Err(_) => {
+            let code = ErrorKind::Internal.code();
+            (code, format!("exit={} reason=panic", code))
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<Outcome> {
     match cli.command {
-        Some(Commands::Init { path }) => {
+        Some(Commands::Init { ref path }) => {
             commands::init::run(path.as_deref())?;
         }
-        Some(Commands::Explain { finding_id, ai }) => {
-            commands::explain::run(&finding_id, ai)?;
+        Some(Commands::Explain { ref finding_id, ai }) => {
+            commands::explain::run(finding_id, ai)?;
         }
-        Some(Commands::Review { ref path }) => {
-            let exit_code = commands::review::run(path.as_deref(), &cli)?;
-            if exit_code == commands::review::ReviewExitCode::FindingsExceedThreshold {
-                std::process::exit(1);
-            }
+        Some(Commands::Review {
+            ref path,
+            stdin_path: Some(ref file),
+            ..
+        }) => {
+            return commands::review::run_stdin(path.as_deref(), file, cli);
         }
-        Some(Commands::Diff { ref base }) => {
-            let exit_code = commands::diff::run(base, &cli)?;
-            if exit_code == commands::review::ReviewExitCode::FindingsExceedThreshold {
-                std::process::exit(1);
-            }
+        Some(Commands::Review { ref path, .. }) => {
+            return commands::review::run(path.as_deref(), cli);
         }
-        Some(Commands::Baseline { ref path, clear }) => {
-            commands::baseline::run(path.as_deref(), clear)?;
+        Some(Commands::Ci {
+            ref path,
+            emit_matcher,
+            upload_sarif,
+        }) => {
+            return commands::ci::run(path.as_deref(), emit_matcher, upload_sarif, cli);
+        }
+        Some(Commands::Import {
+            ref path,
+            ref reports,
+        }) => {
+            return commands::import::run(path.as_deref(), reports, cli);
+        }
+        Some(Commands::Diff { ref args }) => {
+            return commands::diff::run(args, cli);
+        }
+        Some(Commands::Multi {
+            ref workspace,
+            jobs,
+        }) => {
+            return commands::multi::run(workspace.as_deref(), jobs, cli);
+        }
+        Some(Commands::Baseline {
+            command: Some(BaselineCommand::Review { ref path, report }),
+            ..
+        }) => {
+            commands::baseline::review(
+                path.as_deref(),
+                report,
+                cli.single_format()?,
+                cli.no_audit,
+                cli.webhook_dry_run,
+            )?;
+        }
+        Some(Commands::Baseline {
+            command: None,
+            ref path,
+            clear,
+            change,
+            ref reports,
+        }) => {
+            if change {
+                commands::baseline::run_change(path.as_deref(), cli)?;
+            } else {
+                commands::baseline::run(
+                    path.as_deref(),
+                    clear,
+                    reports,
+                    cli.no_audit,
+                    cli.webhook_dry_run,
+                )?;
+            }
         }
         Some(Commands::Watch {
             ref path,
             debounce,
             no_clear,
         }) => {
-            commands::watch::run(path.as_deref(), &cli, debounce, no_clear)?;
+            return commands::watch::run(path.as_deref(), cli, debounce, no_clear);
         }
         Some(Commands::Log { ref show }) => {
             commands::log::run(std::path::Path::new("."), show.as_deref())?;
@@ -42,19 +138,196 @@ fn main() -> Result<()> {
         Some(Commands::Stats { last }) => {
             commands::stats::run(std::path::Path::new("."), last)?;
         }
-        Some(Commands::ConfigCheck) => {
-            commands::config_check::run(std::path::Path::new("."))?;
+        Some(Commands::Trend {
+            ref path,
+            baseline_by_owner,
+            days,
+            ref branch,
+        }) => {
+            commands::trend::run(
+                path.as_deref(),
+                baseline_by_owner,
+                days,
+                branch.as_deref(),
+                cli.single_format()?,
+            )?;
+        }
+        Some(Commands::ConfigCheck { list_rules }) => {
+            commands::config_check::run(std::path::Path::new("."), list_rules)?;
         }
         Some(Commands::Report { ref output, last }) => {
             commands::report::run(std::path::Path::new("."), output, last)?;
         }
-        None => {
-            let exit_code = commands::review::run(None, &cli)?;
-            if exit_code == commands::review::ReviewExitCode::FindingsExceedThreshold {
-                std::process::exit(1);
+        Some(Commands::Fixes { ref file, stdin }) => {
+            commands::fixes::run(file, stdin, cli)?;
+        }
+        Some(Commands::Sbom {
+            ref path,
+            ref output,
+            ref advisories,
+        }) => {
+            commands::sbom::run(
+                path.as_deref(),
+                cli.single_format()?,
+                output.as_deref(),
+                advisories.as_deref(),
+            )?;
+        }
+        Some(Commands::UpgradeReport {
+            ref path,
+            ref targets,
+        }) => {
+            return commands::upgrade_report::run(path.as_deref(), targets, cli.single_format()?);
+        }
+        Some(Commands::Calibrate {
+            ref path,
+            percentile,
+            max_findings,
+            ref output,
+        }) => {
+            commands::calibrate::run(path.as_deref(), percentile, max_findings, output.as_deref())?;
+        }
+        Some(Commands::HashInputs { ref path }) => {
+            commands::hash_inputs::run(path.as_deref(), cli)?;
+        }
+        Some(Commands::Snapshot { ref command }) => match command {
+            SnapshotCommand::Gc {
+                path,
+                keep_last,
+                keep_days,
+            } => {
+                commands::snapshot::gc(path.as_deref(), *keep_last, *keep_days)?;
+            }
+            SnapshotCommand::Changelog {
+                old,
+                new,
+                path,
+                filter,
+                churn_threshold,
+            } => {
+                commands::snapshot::changelog(
+                    path.as_deref(),
+                    old,
+                    new,
+                    *filter,
+                    *churn_threshold,
+                    cli.single_format()?,
+                )?;
+            }
+        },
+        Some(Commands::Issues { ref command }) => match command {
+            IssuesCommand::Sync {
+                path,
+                tracker,
+                min_severity,
+                min_age_runs,
+                rules,
+                branch,
+                dry_run,
+            } => {
+                let options = commands::issues::SyncArgs {
+                    tracker: *tracker,
+                    min_severity: min_severity.as_deref(),
+                    min_age_runs: min_age_runs.map(|n| n as usize),
+                    rules,
+                    branch: branch.as_deref(),
+                    dry_run: *dry_run,
+                };
+                return commands::issues::sync(path.as_deref(), &options);
+            }
+        },
+        Some(Commands::Audit { ref command }) => match command {
+            AuditCommand::Verify { path } => {
+                return commands::audit::verify(path.as_deref());
             }
+            AuditCommand::Show { path, since } => {
+                commands::audit::show(path.as_deref(), since.as_deref(), cli.single_format()?)?;
+            }
+        },
+        Some(Commands::Ignore { ref command }) => match command {
+            IgnoreCommand::Add {
+                id,
+                rule,
+                path,
+                ttl,
+                repo,
+            } => {
+                let target = match id {
+                    Some(id) => commands::ignore::Target::Finding(id),
+                    None => commands::ignore::Target::Rule {
+                        rule: rule.as_deref(),
+                        path: path.as_deref(),
+                    },
+                };
+                commands::ignore::add(repo.as_deref(), target, *ttl)?;
+            }
+            IgnoreCommand::List { path } => {
+                commands::ignore::list(path.as_deref())?;
+            }
+            IgnoreCommand::Clear { path } => {
+                commands::ignore::clear(path.as_deref())?;
+            }
+        },
+        Some(Commands::Daemon { ref command }) => match command {
+            DaemonCommand::Start { path, idle_timeout } => {
+                commands::daemon::start(path.as_deref(), *idle_timeout)?;
+            }
+            DaemonCommand::Run { path, idle_timeout } => {
+                commands::daemon::run(path.as_deref(), *idle_timeout)?;
+            }
+            DaemonCommand::Worker { path, color } => {
+                commands::daemon::work(path.as_deref(), *color, execute)?;
+            }
+            DaemonCommand::Status { path } => {
+                commands::daemon::status(path.as_deref())?;
+            }
+            DaemonCommand::Stop { path } => {
+                commands::daemon::stop(path.as_deref())?;
+            }
+        },
+        Some(Commands::Storage { ref command }) => match command {
+            StorageCommand::Migrate { path } => {
+                commands::storage::migrate(path.as_deref())?;
+            }
+        },
+        Some(Commands::Debug { ref command }) => match command {
+            DebugCommand::Capture {
+                finding,
+                output,
+                path,
+                run,
+                redact,
+                context,
+                yes,
+            } => {
+                let options = commands::debug::CaptureArgs {
+                    finding,
+                    output: output.as_deref(),
+                    run: run.as_deref(),
+                    redact: redact.then_some(*context),
+                    yes: *yes,
+                };
+                commands::debug::capture(path.as_deref(), &options)?;
+            }
+            DebugCommand::Replay { case, trace } => {
+                let replay = commands::debug::replay(case, *trace, cli.single_format()?)?;
+                if !replay.reproduced {
+                    return Ok(Outcome::NotReproduced);
+                }
+            }
+        },
+        #[cfg(feature = "api")]
+        Some(Commands::Api {
+            ref path,
+            bind,
+            allow_remote,
+        }) => {
+            commands::api::run(path.as_deref(), bind, allow_remote)?;
+        }
+        None => {
+            return commands::review::run(None, cli);
         }
     }
 
-    Ok(())
+    Ok(Outcome::Success)
 }
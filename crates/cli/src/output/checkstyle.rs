@@ -0,0 +1,136 @@
+//! Checkstyle XML output formatter, for CI servers that read Checkstyle
+//! reports (e.g. the Jenkins warnings-ng plugin).
+//!
+//! Buffers findings and renders the whole document on
+//! [`finalize`](super::Reporter::finalize): one `<file name="...">` element
+//! per file, in the order files were first reported, with an `<error>` per
+//! finding. `source` is the finding ID under a `revet.` prefix, e.g.
+//! `revet.SEC-001`. Repository-level findings go in a `<file name="">`
+//! element ahead of the others, without a `line`.
+//!
+//! See: <https://checkstyle.sourceforge.io/>
+
+use revet_core::{Finding, Severity};
+use std::path::{Path, PathBuf};
+
+use super::github::relative_path;
+use super::{Out, Reporter, RunEnd};
+
+/// `version` of the `<checkstyle>` root, as written by Checkstyle itself
+pub const CHECKSTYLE_VERSION: &str = "4.3";
+
+pub struct CheckstyleFormatter {
+    repo_path: PathBuf,
+    /// `<error>` elements per relative file; `""` holds repository-level ones
+    files: Vec<(String, Vec<String>)>,
+    out: Out,
+}
+
+impl CheckstyleFormatter {
+    pub fn new(repo_path: PathBuf) -> Self {
+        Self {
+            repo_path,
+            files: Vec::new(),
+            out: Out::stdout(),
+        }
+    }
+
+    /// Print to `out` instead of stdout
+    pub fn with_out(mut self, out: Out) -> Self {
+        self.out = out;
+        self
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str(&format!(
+            "<checkstyle version=\"{}\">\n",
+            CHECKSTYLE_VERSION
+        ));
+        let repository = self.files.iter().filter(|(file, _)| file.is_empty());
+        let files = self.files.iter().filter(|(file, _)| !file.is_empty());
+        for (file, errors) in repository.chain(files) {
+            out.push_str(&format!("  <file name=\"{}\">\n", escape(file)));
+            for error in errors {
+                out.push_str(&format!("    {}\n", error));
+            }
+            out.push_str("  </file>\n");
+        }
+        out.push_str("</checkstyle>\n");
+        out
+    }
+}
+
+/// `<error line=".." column=".." severity=".." message=".." source=".."/>`;
+/// `column` only when known, `line` only for findings in a file
+pub fn format_error(finding: &Finding) -> String {
+    let severity = match finding.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    };
+    let mut out = String::from("<error");
+    if !finding.is_repository_level() {
+        out.push_str(&format!(" line=\"{}\"", finding.line));
+        if let Some(column) = finding.column {
+            out.push_str(&format!(" column=\"{}\"", column));
+        }
+    }
+    out.push_str(&format!(
+        " severity=\"{}\" message=\"{}\" source=\"revet.{}\"/>",
+        severity,
+        escape(&finding.message),
+        escape(&finding.id)
+    ));
+    out
+}
+
+/// Escape `text` for an XML attribute value. Newlines and tabs become
+/// character references so multi-line messages survive attribute
+/// normalisation; other control characters are not allowed in XML 1.0 and
+/// are dropped.
+pub(crate) fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\n' => out.push_str("&#10;"),
+            '\r' => out.push_str("&#13;"),
+            '\t' => out.push_str("&#9;"),
+            c if (c as u32) < 0x20 => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+impl Reporter for CheckstyleFormatter {
+    fn report_finding(&mut self, finding: &Finding, _repo_path: &Path) {
+        let file = if finding.is_repository_level() {
+            String::new()
+        } else {
+            relative_path(finding, &self.repo_path)
+        };
+        let error = format_error(finding);
+        match self.files.iter_mut().find(|(f, _)| *f == file) {
+            Some((_, errors)) => errors.push(error),
+            None => self.files.push((file, vec![error])),
+        }
+    }
+
+    /// The report holds findings only; the summary and policies are left to
+    /// the terminal output
+    fn end_run(&mut self, _end: &RunEnd) {}
+
+    fn finalize(&mut self) {
+        self.out.text(&self.render());
+    }
+
+    fn text(&mut self) -> Option<String> {
+        Some(self.render())
+    }
+}
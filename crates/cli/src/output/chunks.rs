@@ -0,0 +1,472 @@
+//! JSON and SARIF reports written to files with `--output`
+//!
+//! A path that is a directory (or ends in `/`) gets the report in chunks:
+//! `report-1.sarif`, `report-2.sarif`, ... (or `.json`), each a complete
+//! document of its own — every SARIF chunk carries the full `rules` array —
+//! plus `report-index.json` listing the chunks and the range of source paths
+//! each covers. Results are partitioned by source file, so all findings of a
+//! file land in the same chunk, and chunks are filled in path order up to
+//! the `[output.sarif]`/`[output.json]` limits. Chunk files of an earlier
+//! run in the directory are removed first.
+//!
+//! A source file whose findings alone exceed a limit keeps as many as fit,
+//! in priority order: severity first, then line. The rest are dropped and
+//! counted in the index and on stderr, never silently. Any other path gets
+//! one file, and a report over the limits is an error there.
+//!
+//! Sizes are those of the compact serialization the files are written in.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::exit;
+use revet_core::config::OutputConfig;
+
+/// Name of the index of a chunked report
+pub const INDEX_FILE: &str = "report-index.json";
+
+/// Document type of a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Sarif,
+    Json,
+}
+
+impl Kind {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Kind::Sarif => "sarif",
+            Kind::Json => "json",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Kind::Sarif => "SARIF",
+            Kind::Json => "JSON",
+        }
+    }
+
+    fn table(self) -> &'static str {
+        match self {
+            Kind::Sarif => "[output.sarif]",
+            Kind::Json => "[output.json]",
+        }
+    }
+
+    /// The configured limits for this kind of report
+    pub fn limits(self, config: &OutputConfig) -> Limits {
+        match self {
+            Kind::Sarif => Limits {
+                max_results: config.sarif.max_results,
+                max_bytes: config.sarif.max_bytes,
+            },
+            Kind::Json => Limits {
+                max_results: config.json.max_results,
+                max_bytes: config.json.max_bytes,
+            },
+        }
+    }
+
+    /// Where the results array is
+    fn results(self) -> &'static str {
+        match self {
+            Kind::Sarif => "/runs/0/results",
+            Kind::Json => "/findings",
+        }
+    }
+
+    /// Source path of a result
+    fn path(self, result: &Value) -> String {
+        let pointer = match self {
+            Kind::Sarif => "/locations/0/physicalLocation/artifactLocation/uri",
+            Kind::Json => "/file",
+        };
+        result
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Sort key of a result: what survives a dropping comes first
+    fn priority(self, result: &Value) -> (u8, u64) {
+        let (level, line) = match self {
+            Kind::Sarif => ("/level", "/locations/0/physicalLocation/region/startLine"),
+            Kind::Json => ("/severity", "/line"),
+        };
+        let rank = match result.pointer(level).and_then(Value::as_str) {
+            Some("error") => 0,
+            Some("warning") => 1,
+            Some("note" | "info") => 2,
+            _ => 3,
+        };
+        let line = result.pointer(line).and_then(Value::as_u64).unwrap_or(0);
+        (rank, line)
+    }
+
+    /// Mark `document` as chunk `index` of `count`. SARIF chunks also get a
+    /// `runAutomationDetails` id of their own, so a code scanning upload of
+    /// one chunk does not replace the others.
+    fn mark(self, document: &mut Value, index: usize, count: usize) {
+        let chunk = json!({ "index": index, "count": count });
+        match self {
+            Kind::Sarif => {
+                let Some(run) = document
+                    .pointer_mut("/runs/0")
+                    .and_then(Value::as_object_mut)
+                else {
+                    return;
+                };
+                run.insert(
+                    "automationDetails".to_string(),
+                    json!({ "id": format!("revet/chunk-{}/", index) }),
+                );
+                let properties = run.entry("properties").or_insert_with(|| json!({}));
+                if let Some(properties) = properties.as_object_mut() {
+                    properties.insert("chunk".to_string(), chunk);
+                }
+            }
+            Kind::Json => {
+                if let Some(object) = document.as_object_mut() {
+                    object.insert("chunk".to_string(), chunk);
+                }
+            }
+        }
+    }
+}
+
+/// Largest file `--output` writes; 0 means no limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    pub max_results: usize,
+    pub max_bytes: u64,
+}
+
+impl Limits {
+    fn fits(&self, results: usize, bytes: u64) -> bool {
+        (self.max_results == 0 || results <= self.max_results)
+            && (self.max_bytes == 0 || bytes <= self.max_bytes)
+    }
+}
+
+/// One chunk in the index
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkEntry {
+    pub file: String,
+    pub results: usize,
+    pub bytes: u64,
+    /// First and last source path of the chunk's results, in path order
+    pub first_path: String,
+    pub last_path: String,
+}
+
+/// Results of one source file left out because they did not fit a chunk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DroppedResults {
+    pub path: String,
+    pub count: usize,
+}
+
+/// `report-index.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportIndex {
+    /// `sarif` or `json`
+    pub format: String,
+    pub max_results: usize,
+    pub max_bytes: u64,
+    pub total_results: usize,
+    pub written_results: usize,
+    pub dropped_results: usize,
+    pub chunks: Vec<ChunkEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dropped: Vec<DroppedResults>,
+}
+
+/// A report split into chunks
+#[derive(Debug)]
+pub struct Split {
+    /// Compact documents with their index entries (named on writing)
+    pub chunks: Vec<(String, ChunkEntry)>,
+    pub dropped: Vec<DroppedResults>,
+    pub total_results: usize,
+}
+
+/// What `--output` wrote
+#[derive(Debug)]
+pub struct Written {
+    /// Report files, in chunk order
+    pub files: Vec<PathBuf>,
+    /// The index, for a chunked report
+    pub index: Option<ReportIndex>,
+}
+
+/// Whether `path` names a directory for a chunked report
+pub fn is_directory(path: &Path) -> bool {
+    path.is_dir() || path.as_os_str().to_string_lossy().ends_with(['/', '\\'])
+}
+
+/// Why `document` does not fit one file, if it does not
+pub fn overflow(document: &Value, kind: Kind, limits: Limits) -> Option<String> {
+    let results = results_of(document, kind).len();
+    let bytes = serde_json::to_vec(document).map_or(0, |b| b.len() as u64);
+    if limits.fits(results, bytes) {
+        return None;
+    }
+    let mut over = Vec::new();
+    if limits.max_results != 0 && results > limits.max_results {
+        over.push(format!(
+            "{} results, over max_results = {}",
+            results, limits.max_results
+        ));
+    }
+    if limits.max_bytes != 0 && bytes > limits.max_bytes {
+        over.push(format!(
+            "{} bytes, over max_bytes = {}",
+            bytes, limits.max_bytes
+        ));
+    }
+    Some(format!(
+        "the {} report has {} {}",
+        kind.label(),
+        over.join(" and "),
+        kind.table()
+    ))
+}
+
+/// Write `document` to `path`: chunks and an index in a directory, or one
+/// file
+pub fn write(document: Value, kind: Kind, path: &Path, limits: Limits) -> Result<Written> {
+    if !is_directory(path) {
+        if let Some(reason) = overflow(&document, kind, limits) {
+            return Err(exit::usage(format!(
+                "{}; pass a directory to --output to split it into chunks",
+                reason
+            )));
+        }
+        std::fs::write(path, serde_json::to_vec(&document)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        return Ok(Written {
+            files: vec![path.to_path_buf()],
+            index: None,
+        });
+    }
+
+    std::fs::create_dir_all(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    remove_stale(path, kind)?;
+    let split = split(&document, kind, limits);
+    let mut files = Vec::new();
+    let mut entries = Vec::new();
+    for (i, (json, mut entry)) in split.chunks.into_iter().enumerate() {
+        entry.file = format!("report-{}.{}", i + 1, kind.extension());
+        let file = path.join(&entry.file);
+        std::fs::write(&file, json)
+            .with_context(|| format!("Failed to write {}", file.display()))?;
+        files.push(file);
+        entries.push(entry);
+    }
+    let dropped_results = split.dropped.iter().map(|d| d.count).sum();
+    let index = ReportIndex {
+        format: kind.extension().to_string(),
+        max_results: limits.max_results,
+        max_bytes: limits.max_bytes,
+        total_results: split.total_results,
+        written_results: split.total_results - dropped_results,
+        dropped_results,
+        chunks: entries,
+        dropped: split.dropped,
+    };
+    let index_file = path.join(INDEX_FILE);
+    std::fs::write(&index_file, serde_json::to_string_pretty(&index)?)
+        .with_context(|| format!("Failed to write {}", index_file.display()))?;
+    Ok(Written {
+        files,
+        index: Some(index),
+    })
+}
+
+/// Split `document` into chunks within `limits`, by source file
+pub fn split(document: &Value, kind: Kind, limits: Limits) -> Split {
+    let results = results_of(document, kind);
+    let total_results = results.len();
+
+    // The document without results, marked with the widest chunk numbers:
+    // every chunk's own marks are no longer
+    let mut base = document.clone();
+    if let Some(array) = base.pointer_mut(kind.results()) {
+        *array = json!([]);
+    }
+    let mut widest = base.clone();
+    kind.mark(&mut widest, usize::MAX, usize::MAX);
+    let base_bytes = serde_json::to_vec(&widest).map_or(0, |b| b.len() as u64);
+    // The results array and the commas between its items
+    let size = |items: usize, bytes: u64| base_bytes + bytes + items.saturating_sub(1) as u64;
+
+    let mut files: BTreeMap<String, Vec<(Value, u64)>> = BTreeMap::new();
+    for result in results {
+        let bytes = serde_json::to_vec(&result).map_or(0, |b| b.len() as u64);
+        files
+            .entry(kind.path(&result))
+            .or_default()
+            .push((result, bytes));
+    }
+
+    let mut dropped = Vec::new();
+    let mut groups: Vec<Group> = Vec::new();
+    let mut current = Group::default();
+    for (path, mut items) in files {
+        let mut bytes: u64 = items.iter().map(|(_, b)| b).sum();
+        if !limits.fits(items.len(), size(items.len(), bytes)) {
+            let count = keep_within(kind, &mut items, limits, size);
+            dropped.push(DroppedResults {
+                path: path.clone(),
+                count,
+            });
+            bytes = items.iter().map(|(_, b)| b).sum();
+        }
+        if items.is_empty() {
+            continue;
+        }
+        let count = current.1 + items.len();
+        if !current.0.is_empty() && !limits.fits(count, size(count, current.2 + bytes)) {
+            groups.push(std::mem::take(&mut current));
+        }
+        current.1 += items.len();
+        current.2 += bytes;
+        current.0.push((path, items));
+    }
+    if !current.0.is_empty() || groups.is_empty() {
+        groups.push(current);
+    }
+
+    let count = groups.len();
+    let chunks = groups
+        .into_iter()
+        .enumerate()
+        .map(|(i, (group, results, _))| {
+            let first_path = group.first().map(|(p, _)| p.clone()).unwrap_or_default();
+            let last_path = group.last().map(|(p, _)| p.clone()).unwrap_or_default();
+            let mut chunk = base.clone();
+            if let Some(array) = chunk.pointer_mut(kind.results()) {
+                *array = Value::Array(
+                    group
+                        .into_iter()
+                        .flat_map(|(_, items)| items.into_iter().map(|(r, _)| r))
+                        .collect(),
+                );
+            }
+            kind.mark(&mut chunk, i + 1, count);
+            let json = serde_json::to_string(&chunk).unwrap_or_default();
+            let entry = ChunkEntry {
+                file: String::new(),
+                results,
+                bytes: json.len() as u64,
+                first_path,
+                last_path,
+            };
+            (json, entry)
+        })
+        .collect();
+    Split {
+        chunks,
+        dropped,
+        total_results,
+    }
+}
+
+/// Source files with their results (and each result's size), and the
+/// total count and size of those results
+type Group = (Vec<(String, Vec<(Value, u64)>)>, usize, u64);
+
+/// Trim one file's results to the highest-priority ones that fit a chunk
+/// alone, keeping their order; returns how many were dropped
+fn keep_within(
+    kind: Kind,
+    items: &mut Vec<(Value, u64)>,
+    limits: Limits,
+    size: impl Fn(usize, u64) -> u64,
+) -> usize {
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by_key(|&i| (kind.priority(&items[i].0), i));
+    let mut keep = vec![false; items.len()];
+    let (mut count, mut bytes) = (0, 0);
+    for i in order {
+        if !limits.fits(count + 1, size(count + 1, bytes + items[i].1)) {
+            break;
+        }
+        keep[i] = true;
+        count += 1;
+        bytes += items[i].1;
+    }
+    let before = items.len();
+    let mut flags = keep.into_iter();
+    items.retain(|_| flags.next().unwrap_or(false));
+    before - items.len()
+}
+
+fn results_of(document: &Value, kind: Kind) -> Vec<Value> {
+    document
+        .pointer(kind.results())
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Remove the chunks and index an earlier run left in `dir`
+fn remove_stale(dir: &Path, kind: Kind) -> Result<()> {
+    let suffix = format!(".{}", kind.extension());
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let chunk = name
+            .strip_prefix("report-")
+            .and_then(|rest| rest.strip_suffix(&suffix))
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()));
+        if chunk || name == INDEX_FILE {
+            std::fs::remove_file(entry.path())
+                .with_context(|| format!("Failed to remove {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// The stderr lines for what was written
+pub fn print_summary(written: &Written, kind: Kind) {
+    let Some(index) = &written.index else {
+        if let Some(file) = written.files.first() {
+            eprintln!("  {} {}", "Report:".bold(), file.display());
+        }
+        return;
+    };
+    let dir = written
+        .files
+        .first()
+        .and_then(|f| f.parent())
+        .map_or_else(String::new, |d| d.display().to_string());
+    eprintln!(
+        "  {} {} {} file{} with {} results in {} (index: {})",
+        "Report:".bold(),
+        index.chunks.len(),
+        kind.label(),
+        if index.chunks.len() == 1 { "" } else { "s" },
+        index.written_results,
+        dir,
+        INDEX_FILE
+    );
+    if index.dropped_results > 0 {
+        eprintln!(
+            "  {}: {} results dropped — a file's findings must stay in one chunk, and {} {} no room for all of them:",
+            "warn".yellow(),
+            index.dropped_results,
+            kind.table(),
+            if index.dropped.len() == 1 { "leaves" } else { "leave" },
+        );
+        for dropped in &index.dropped {
+            eprintln!("    {} ({} dropped)", dropped.path, dropped.count);
+        }
+    }
+}
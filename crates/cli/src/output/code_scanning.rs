@@ -0,0 +1,112 @@
+//! SARIF uploads to GitHub code scanning (`revet ci --upload-sarif`)
+//!
+//! Each file — one report, or every chunk of a chunked one — goes to
+//! `POST /repos/{owner}/{repo}/code-scanning/sarifs` gzipped and
+//! base64-encoded, one after another, so an upload limit hit by one chunk
+//! stops the rest rather than leaving an unknown subset behind. Chunks carry
+//! their own `automationDetails` id and do not replace each other.
+
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use colored::Colorize;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Where and as what to upload
+#[derive(Debug, Clone)]
+pub struct UploadContext {
+    /// `GITHUB_API_URL`, default `https://api.github.com`
+    pub api_url: String,
+    pub token: String,
+    /// `owner/repo`
+    pub repository: String,
+    pub commit_sha: String,
+    /// `GITHUB_REF`, e.g. `refs/heads/main` or `refs/pull/7/merge`
+    pub git_ref: String,
+}
+
+impl UploadContext {
+    /// Read the context from the GitHub Actions environment; `None` when a
+    /// variable is missing
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        Some(Self {
+            api_url: var("GITHUB_API_URL").unwrap_or_else(|| "https://api.github.com".to_string()),
+            token: var("GITHUB_TOKEN")?,
+            repository: var("GITHUB_REPOSITORY")?,
+            commit_sha: var("GITHUB_SHA")?,
+            git_ref: var("GITHUB_REF")?,
+        })
+    }
+}
+
+/// Upload `files` in order; returns the SARIF upload id of each
+pub fn upload(files: &[PathBuf], ctx: &UploadContext) -> Result<Vec<String>> {
+    let client = reqwest::blocking::Client::builder()
+        .user_agent(format!("revet/{}", revet_core::VERSION))
+        .build()?;
+    let url = format!(
+        "{}/repos/{}/code-scanning/sarifs",
+        ctx.api_url.trim_end_matches('/'),
+        ctx.repository
+    );
+    let mut ids = Vec::new();
+    for (i, file) in files.iter().enumerate() {
+        let sarif =
+            std::fs::read(file).with_context(|| format!("Failed to read {}", file.display()))?;
+        let body = serde_json::json!({
+            "commit_sha": ctx.commit_sha,
+            "ref": ctx.git_ref,
+            "sarif": encode(&sarif)?,
+            "tool_name": "revet",
+        });
+        let resp = client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", ctx.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&body)
+            .send()
+            .with_context(|| format!("Failed to upload {}", file.display()))?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().unwrap_or_default();
+            bail!(
+                "GitHub rejected {} ({} of {} uploaded): {} {}",
+                file.display(),
+                i,
+                files.len(),
+                status,
+                text
+            );
+        }
+        let id = resp
+            .json::<serde_json::Value>()
+            .ok()
+            .and_then(|v| v["id"].as_str().map(str::to_string))
+            .unwrap_or_default();
+        eprintln!(
+            "  {} {} ({}/{}){}",
+            "Uploaded".green(),
+            file.display(),
+            i + 1,
+            files.len(),
+            if id.is_empty() {
+                String::new()
+            } else {
+                format!(" — id {}", id)
+            }
+        );
+        ids.push(id);
+    }
+    Ok(ids)
+}
+
+/// The `sarif` field: gzip, then base64
+pub fn encode(sarif: &[u8]) -> Result<String> {
+    let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+    gz.write_all(sarif)?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(gz.finish()?))
+}
@@ -1,74 +1,238 @@
 //! GitHub Actions workflow command output formatter.
 //!
 //! Produces `::error`, `::warning`, and `::notice` annotations for inline
-//! PR feedback. Each finding is printed immediately — no buffering needed.
+//! PR feedback. Annotations are buffered and printed per file, each file
+//! wrapped in a `::group::` so the job log collapses. Repository-level
+//! findings have no file to annotate and become workflow notices, printed
+//! ahead of the groups.
 //!
 //! See: <https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions>
 
-use revet_core::{BlastRadiusSummary, Finding, ReviewSummary, Severity, SuppressedFinding};
+use revet_core::policy::Action;
+use revet_core::{BlastRadiusSummary, Finding, PolicyReport, RiskLevel, RiskReport, Severity};
 use std::path::{Path, PathBuf};
-use std::time::Duration;
 
-use super::OutputFormatter;
+use super::{Out, Reporter, RunEnd, RunStart};
 
 pub struct GithubFormatter {
     repo_path: PathBuf,
+    groups: FileGroups,
+    out: Out,
 }
 
 impl GithubFormatter {
     pub fn new(repo_path: PathBuf) -> Self {
-        Self { repo_path }
+        Self {
+            repo_path,
+            groups: FileGroups::default(),
+            out: Out::stdout(),
+        }
+    }
+
+    /// Print to `out` instead of stdout
+    pub fn with_out(mut self, out: Out) -> Self {
+        self.out = out;
+        self
     }
 }
 
-impl OutputFormatter for GithubFormatter {
-    fn write_blast_radius(&mut self, summary: &BlastRadiusSummary) {
-        // Emit a GitHub Actions notice annotation with the blast radius summary
-        println!(
-            "::notice title=PR Blast Radius::Risk: {} | {} symbol(s) modified | {} caller(s) affected | {} module {}",
-            summary.risk,
-            summary.directly_modified,
-            summary.transitively_affected,
-            summary.cross_module_crossings,
-            if summary.cross_module_crossings == 1 { "boundary crossed" } else { "boundaries crossed" },
-        );
+/// Output lines collected per file, in the order files were first seen
+#[derive(Default)]
+pub(crate) struct FileGroups {
+    files: Vec<(String, Vec<String>)>,
+    /// Lines of repository-level findings (empty `file`)
+    repository: Vec<String>,
+}
+
+impl FileGroups {
+    pub(crate) fn push(&mut self, file: String, line: String) {
+        if file.is_empty() {
+            self.repository.push(line);
+            return;
+        }
+        match self.files.iter_mut().find(|(f, _)| *f == file) {
+            Some((_, lines)) => lines.push(line),
+            None => self.files.push((file, vec![line])),
+        }
     }
 
-    fn write_finding(&mut self, finding: &Finding, _repo_path: &Path) {
-        println!("{}", format_finding(finding, &self.repo_path));
+    /// Print the repository-level lines, then every file's lines between
+    /// `::group::<file>` and `::endgroup::`
+    pub(crate) fn flush(&mut self, out: &Out) {
+        for line in self.repository.drain(..) {
+            outln!(out, "{}", line);
+        }
+        for (file, lines) in self.files.drain(..) {
+            outln!(out, "::group::{}", escape_data(&file));
+            for line in lines {
+                outln!(out, "{}", line);
+            }
+            outln!(out, "::endgroup::");
+        }
     }
+}
 
-    fn write_summary(
-        &mut self,
-        _summary: &ReviewSummary,
-        _suppressed: &[SuppressedFinding],
-        _elapsed: Duration,
-        _run_id: Option<&str>,
-    ) {
+/// Finding path relative to the repository, with `/` separators
+pub(crate) fn relative_path(finding: &Finding, repo_path: &Path) -> String {
+    finding
+        .file
+        .strip_prefix(repo_path)
+        .unwrap_or(&finding.file)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+impl Reporter for GithubFormatter {
+    fn begin_run(&mut self, run: &RunStart) {
+        if let Some(summary) = run.blast_radius {
+            write_blast_radius(&self.out, summary);
+        }
+        if let Some(risk) = run.risk {
+            write_risk(&self.out, risk);
+        }
+    }
+
+    fn report_finding(&mut self, finding: &Finding, _repo_path: &Path) {
+        let file = relative_path(finding, &self.repo_path);
+        self.groups
+            .push(file, format_finding(finding, &self.repo_path));
+    }
+
+    fn end_run(&mut self, end: &RunEnd) {
         // GitHub annotations don't have a summary section.
+        if let Some(report) = end.policies {
+            write_policies(&self.out, report);
+        }
     }
 
-    fn write_no_files(&mut self, _elapsed: Duration) {
-        // Nothing to annotate.
+    fn finalize(&mut self) {
+        self.groups.flush(&self.out);
     }
 }
 
+fn write_blast_radius(out: &Out, summary: &BlastRadiusSummary) {
+    // Emit a GitHub Actions notice annotation with the blast radius summary
+    outln!(out,
+        "::notice title=PR Blast Radius::Risk: {} | {} symbol(s) modified | {} caller(s) affected | {} module {}",
+        summary.risk,
+        summary.directly_modified,
+        summary.transitively_affected,
+        summary.cross_module_crossings,
+        if summary.cross_module_crossings == 1 { "boundary crossed" } else { "boundaries crossed" },
+    );
+}
+
+fn write_risk(out: &Out, risk: &RiskReport) {
+    let level = match risk.level {
+        RiskLevel::High => "warning",
+        RiskLevel::Medium | RiskLevel::Low => "notice",
+    };
+    outln!(out, "::{} title=Change Risk::{}", level, risk.headline());
+}
+
+fn write_policies(out: &Out, report: &PolicyReport) {
+    for line in policy_lines(report) {
+        let level = match (line.passed, line.action) {
+            (true, _) => "notice",
+            (false, Action::Fail) => "error",
+            (false, _) => "warning",
+        };
+        outln!(
+            out,
+            "::{} title={}::{}",
+            level,
+            escape_property(&format!("Policy {}", line.name)),
+            escape_data(&line.text)
+        );
+    }
+}
+
+/// One policy result as a line of text, shared with the problem-matcher
+/// format
+pub(crate) struct PolicyLine<'a> {
+    pub name: &'a str,
+    pub action: Action,
+    pub passed: bool,
+    /// e.g. `failed: 3 matching finding(s), violated when count > 0`
+    pub text: String,
+}
+
+pub(crate) fn policy_lines(report: &PolicyReport) -> impl Iterator<Item = PolicyLine<'_>> {
+    report.results.iter().map(|r| {
+        let outcome = match (r.passed, r.action) {
+            (true, _) => "passed",
+            (false, Action::Fail) => "failed",
+            (false, Action::Warn) => "warning",
+            (false, Action::RequireEscalation) => "requires escalation",
+        };
+        let mut text = format!(
+            "{}: {} matching finding(s), violated when {}",
+            outcome, r.count, r.condition
+        );
+        if let (false, Some(description)) = (r.passed, &r.description) {
+            text.push_str(&format!(" ({})", description));
+        }
+        PolicyLine {
+            name: &r.name,
+            action: r.action,
+            passed: r.passed,
+            text,
+        }
+    })
+}
+
 pub fn format_finding(finding: &Finding, repo_path: &Path) -> String {
+    if finding.is_repository_level() {
+        return format!(
+            "::notice title={}::{}",
+            escape_property(&finding.id),
+            escape_data(&annotation_message(finding))
+        );
+    }
     let level = match finding.severity {
         Severity::Error => "error",
         Severity::Warning => "warning",
         Severity::Info => "notice",
     };
-    let rel_path = finding
-        .file
-        .strip_prefix(repo_path)
-        .unwrap_or(&finding.file);
+    let mut position = format!("line={}", finding.line);
+    if let Some(column) = finding.column {
+        position.push_str(&format!(",col={}", column));
+    }
+    if let Some(end_column) = finding.end_column {
+        position.push_str(&format!(",endColumn={}", end_column));
+    }
     format!(
-        "::{level} file={},line={},title={}::{msg}",
-        rel_path.display(),
-        finding.line,
-        finding.id,
+        "::{level} file={},{},title={}::{msg}",
+        escape_property(&relative_path(finding, repo_path)),
+        position,
+        escape_property(&finding.id),
         level = level,
-        msg = finding.message,
+        msg = escape_data(&annotation_message(finding)),
     )
 }
+
+/// The headline, pointing to `revet explain` when the finding has details
+/// an annotation has no room for
+fn annotation_message(finding: &Finding) -> String {
+    match &finding.details {
+        Some(details) if !details.is_empty() => format!(
+            "{} (details: run `revet explain {}`)",
+            finding.message, finding.id
+        ),
+        _ => finding.message.clone(),
+    }
+}
+
+/// Escape a workflow command's message: `%`, CR and LF
+pub fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value: as [`escape_data`], plus `:`
+/// and `,`
+pub fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
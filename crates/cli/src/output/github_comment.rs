@@ -19,7 +19,7 @@
 
 use anyhow::{bail, Context, Result};
 use reqwest::blocking::Client;
-use revet_core::Finding;
+use revet_core::{Finding, Locale, RiskReport};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
@@ -27,6 +27,12 @@ use std::path::Path;
 /// comments on re-runs without hitting a separate API endpoint.
 const MARKER_PREFIX: &str = "<!-- revet:";
 
+/// Marker identifying the single risk summary comment revet keeps on a PR.
+const RISK_MARKER: &str = "<!-- revet:risk -->";
+
+/// Changed symbols listed in the risk summary table
+const RISK_TABLE_ROWS: usize = 10;
+
 /// Context needed to call the GitHub API.
 #[derive(Debug, Clone)]
 pub struct GitHubContext {
@@ -80,6 +86,17 @@ struct ExistingComment {
     body: String,
 }
 
+#[derive(Deserialize)]
+struct ExistingIssueComment {
+    id: u64,
+    body: String,
+}
+
+#[derive(Serialize)]
+struct IssueCommentBody<'a> {
+    body: &'a str,
+}
+
 #[derive(Serialize)]
 struct NewComment<'a> {
     body: String,
@@ -101,6 +118,7 @@ pub fn post_review_comments(
     findings: &[Finding],
     repo_root: &Path,
     ctx: &GitHubContext,
+    locale: Locale,
 ) -> Result<(usize, usize, usize)> {
     let client = Client::builder()
         .user_agent("revet-cli/0.1")
@@ -137,7 +155,7 @@ pub fn post_review_comments(
             .unwrap_or(&finding.file);
 
         let path_str = rel_path.to_string_lossy();
-        let body = format_comment_body(finding);
+        let body = format_comment_body(finding, locale);
 
         let comment = NewComment {
             body,
@@ -162,8 +180,100 @@ pub fn post_review_comments(
     Ok((posted, skipped_off_diff, skipped_duplicate))
 }
 
+/// Create or update the PR's risk summary comment (a regular PR comment, not
+/// an inline review comment). Returns `true` if an existing comment was updated.
+pub fn post_risk_summary(risk: &RiskReport, ctx: &GitHubContext) -> Result<bool> {
+    let client = Client::builder()
+        .user_agent("revet-cli/0.1")
+        .build()
+        .context("Failed to build HTTP client")?;
+    let body = format_risk_comment(risk);
+
+    let url = ctx.api_url(&format!("issues/{}/comments?per_page=100", ctx.pr_number));
+    let existing: Vec<ExistingIssueComment> = github_request(client.get(&url), ctx)?
+        .json()
+        .context("Failed to parse existing PR comments")?;
+
+    match existing.iter().find(|c| c.body.contains(RISK_MARKER)) {
+        Some(comment) => {
+            let url = ctx.api_url(&format!("issues/comments/{}", comment.id));
+            github_request(
+                client.patch(&url).json(&IssueCommentBody { body: &body }),
+                ctx,
+            )?;
+            Ok(true)
+        }
+        None => {
+            let url = ctx.api_url(&format!("issues/{}/comments", ctx.pr_number));
+            github_request(
+                client.post(&url).json(&IssueCommentBody { body: &body }),
+                ctx,
+            )?;
+            Ok(false)
+        }
+    }
+}
+
+/// Render the risk report as a markdown PR comment with a per-symbol table.
+pub fn format_risk_comment(risk: &RiskReport) -> String {
+    let emoji = match risk.level {
+        revet_core::RiskLevel::High => "🔴",
+        revet_core::RiskLevel::Medium => "🟡",
+        revet_core::RiskLevel::Low => "🟢",
+    };
+    let mut body = format!("{} **revet** — {}\n", emoji, risk.headline());
+
+    if !risk.symbols.is_empty() {
+        body.push_str(
+            "\n| Symbol | File | Score | Direct fan-in | Transitive | Critical |\n\
+             |--------|------|------:|--------------:|-----------:|---------:|\n",
+        );
+        for symbol in risk.symbols.iter().take(RISK_TABLE_ROWS) {
+            body.push_str(&format!(
+                "| `{}` | `{}:{}` | {} ({}) | {} | {} | {} |\n",
+                symbol.display_name(),
+                symbol.file.display(),
+                symbol.line,
+                symbol.score,
+                symbol.level.to_string().to_lowercase(),
+                symbol.direct_fan_in,
+                symbol.transitive_dependents,
+                symbol.critical_dependents,
+            ));
+        }
+        if risk.symbols.len() > RISK_TABLE_ROWS {
+            body.push_str(&format!(
+                "\n_{} more changed symbol(s) not shown._\n",
+                risk.symbols.len() - RISK_TABLE_ROWS
+            ));
+        }
+    }
+
+    body.push_str(&format!("\n{}", RISK_MARKER));
+    body
+}
+
 // ── Helpers ──────────────────────────────────────────────────────────────────
 
+fn github_request(
+    request: reqwest::blocking::RequestBuilder,
+    ctx: &GitHubContext,
+) -> Result<reqwest::blocking::Response> {
+    let resp = request
+        .header("Authorization", format!("Bearer {}", ctx.token))
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .context("GitHub API request failed")?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().unwrap_or_default();
+        bail!("GitHub API error {}: {}", status, text);
+    }
+    Ok(resp)
+}
+
 fn fetch_existing_comments(client: &Client, ctx: &GitHubContext) -> Result<Vec<ExistingComment>> {
     let url = ctx.api_url(&format!("pulls/{}/comments?per_page=100", ctx.pr_number));
 
@@ -207,7 +317,7 @@ fn post_comment(client: &Client, ctx: &GitHubContext, comment: &NewComment) -> R
 }
 
 /// Embed the finding ID as an invisible HTML comment for deduplication.
-fn format_comment_body(finding: &Finding) -> String {
+pub fn format_comment_body(finding: &Finding, locale: Locale) -> String {
     let severity_emoji = match finding.severity {
         revet_core::Severity::Error => "🔴",
         revet_core::Severity::Warning => "🟡",
@@ -216,12 +326,17 @@ fn format_comment_body(finding: &Finding) -> String {
 
     let mut body = format!(
         "{} **revet [{}]**: {}\n",
-        severity_emoji, finding.id, finding.message
+        severity_emoji,
+        finding.id,
+        finding.localized_message(locale)
     );
 
-    if let Some(ref suggestion) = finding.suggestion {
+    if let Some(suggestion) = finding.localized_suggestion(locale) {
         body.push_str(&format!("\n> **Suggestion:** {}\n", suggestion));
     }
+    if let Some(code) = &finding.suggestion_code {
+        body.push_str(&format!("\n```{}\n{}\n```\n", code.language, code.snippet));
+    }
 
     // Invisible marker for deduplication on re-runs
     body.push_str(&format!("\n{}{} -->", MARKER_PREFIX, finding.id));
@@ -0,0 +1,87 @@
+//! Plain-text formatter for GitHub Actions problem matchers.
+//!
+//! Prints one `file:line:col: severity ID: message` line per finding, grouped
+//! per file like the `github` format. Registered with the bundled matcher
+//! (`revet ci --emit-matcher`), the runner turns these lines into annotations
+//! itself — no workflow commands or API calls involved, so it also works on
+//! fork pull requests.
+//!
+//! See: <https://github.com/actions/toolkit/blob/main/docs/problem-matchers.md>
+
+use revet_core::{Finding, Severity};
+use std::path::{Path, PathBuf};
+
+use super::github::{policy_lines, relative_path, FileGroups};
+use super::{Out, Reporter, RunEnd};
+
+/// Problem matcher definition matching [`format_finding`] output
+pub const MATCHER_JSON: &str = include_str!("revet-matcher.json");
+
+/// `owner` of the bundled matcher, for `::remove-matcher owner=revet::`
+pub const MATCHER_OWNER: &str = "revet";
+
+/// File name the matcher is written to before `::add-matcher::`
+pub const MATCHER_FILE: &str = "revet-matcher.json";
+
+pub struct GithubMatcherFormatter {
+    repo_path: PathBuf,
+    groups: FileGroups,
+    out: Out,
+}
+
+impl GithubMatcherFormatter {
+    pub fn new(repo_path: PathBuf) -> Self {
+        Self {
+            repo_path,
+            groups: FileGroups::default(),
+            out: Out::stdout(),
+        }
+    }
+
+    /// Print to `out` instead of stdout
+    pub fn with_out(mut self, out: Out) -> Self {
+        self.out = out;
+        self
+    }
+}
+
+impl Reporter for GithubMatcherFormatter {
+    fn report_finding(&mut self, finding: &Finding, _repo_path: &Path) {
+        let file = relative_path(finding, &self.repo_path);
+        self.groups
+            .push(file, format_finding(finding, &self.repo_path));
+    }
+
+    /// The job summary carries the totals. Policy lines carry no
+    /// `file:line:`, so the matcher leaves them as log text
+    fn end_run(&mut self, end: &RunEnd) {
+        for line in end.policies.into_iter().flat_map(policy_lines) {
+            outln!(self.out, "policy {}: {}", line.name, line.text);
+        }
+    }
+
+    fn finalize(&mut self) {
+        self.groups.flush(&self.out);
+    }
+}
+
+/// `src/config.ts:9:5: error SEC-001: message` — the column is left out
+/// when unknown, and the message is kept on one line. Repository-level
+/// findings print as `repository: warning REPO-001: message`, which the
+/// matcher leaves as log text since there is no file to annotate.
+pub fn format_finding(finding: &Finding, repo_path: &Path) -> String {
+    let level = match finding.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "notice",
+    };
+    let message = finding.message.lines().collect::<Vec<_>>().join(" ");
+    if finding.is_repository_level() {
+        return format!("repository: {} {}: {}", level, finding.id, message);
+    }
+    let mut position = format!("{}:{}", relative_path(finding, repo_path), finding.line);
+    if let Some(column) = finding.column {
+        position.push_str(&format!(":{}", column));
+    }
+    format!("{}: {} {}: {}", position, level, finding.id, message)
+}
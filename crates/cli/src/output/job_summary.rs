@@ -0,0 +1,193 @@
+//! Markdown report for the GitHub Actions job summary.
+//!
+//! Appended to the file named by `$GITHUB_STEP_SUMMARY`, which the runner
+//! renders on the workflow run page. One table of findings sorted by file
+//! and line, repository-level findings first, capped at [`MAX_ROWS`] so
+//! large runs stay readable. Changes to revet's own configuration (`CONF`)
+//! are listed above it, in a section of their own.
+
+use anyhow::{Context, Result};
+use revet_core::analyzer::registry::prefix_of;
+use revet_core::{Finding, FindingLocation, Locale, PolicyReport, ReviewSummary, Severity};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::github::relative_path;
+
+/// Environment variable naming the job summary file
+pub const STEP_SUMMARY_ENV: &str = "GITHUB_STEP_SUMMARY";
+
+/// Findings listed in the table; the rest are counted below it
+pub const MAX_ROWS: usize = 200;
+
+/// Prefix of the findings listed in their own section
+const CONFIG_DRIFT_PREFIX: &str = "CONF";
+
+/// Render the report. `passed` is the run's gate / fail-on / policy result.
+pub fn render(
+    findings: &[Finding],
+    summary: &ReviewSummary,
+    suppressed: usize,
+    policies: &PolicyReport,
+    repo_path: &Path,
+    passed: bool,
+    locale: Locale,
+) -> String {
+    let mut out = String::new();
+    out.push_str("## revet review\n\n");
+    out.push_str(&format!(
+        "**{}** · {} {} · {} {} · {} info · {} {} analyzed\n\n",
+        if passed { "Passed" } else { "Failed" },
+        summary.errors,
+        plural(summary.errors, "error", "errors"),
+        summary.warnings,
+        plural(summary.warnings, "warning", "warnings"),
+        summary.info,
+        summary.files_analyzed,
+        plural(summary.files_analyzed, "file", "files"),
+    ));
+
+    // Changes to revet's own configuration come first, on their own: they
+    // decide what every other row is measured against
+    let (config, findings): (Vec<&Finding>, Vec<&Finding>) = findings
+        .iter()
+        .partition(|f| prefix_of(&f.id) == CONFIG_DRIFT_PREFIX);
+    if !config.is_empty() {
+        out.push_str("### Configuration changes\n\n");
+        out.push_str("| File | Line | Severity | Message |\n");
+        out.push_str("|------|-----:|----------|---------|\n");
+        for f in &config {
+            out.push_str(&format!(
+                "| `{}` | {} | {} | {} |\n",
+                relative_path(f, repo_path).replace('`', "'"),
+                f.line,
+                severity_label(f.severity),
+                message_cell(f, locale),
+            ));
+        }
+        out.push('\n');
+    }
+
+    if findings.is_empty() && config.is_empty() {
+        out.push_str("No findings.\n");
+    } else if !findings.is_empty() {
+        let mut rows = findings;
+        rows.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+        out.push_str("| File | Line | Severity | ID | Message |\n");
+        out.push_str("|------|-----:|----------|----|---------|\n");
+        for f in rows.iter().take(MAX_ROWS) {
+            let (file, line) = match f.location() {
+                FindingLocation::File { line, .. } => (
+                    format!("`{}`", relative_path(f, repo_path).replace('`', "'")),
+                    line.to_string(),
+                ),
+                FindingLocation::Repository => ("_repository_".to_string(), "—".to_string()),
+            };
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                file,
+                line,
+                severity_label(f.severity),
+                f.id,
+                message_cell(f, locale),
+            ));
+        }
+        if rows.len() > MAX_ROWS {
+            out.push_str(&format!(
+                "\n…and {} more — see the job log for every finding.\n",
+                rows.len() - MAX_ROWS
+            ));
+        }
+    }
+
+    if !policies.is_empty() {
+        out.push_str("\n| Policy | Action | Matching | Violated when | Result |\n");
+        out.push_str("|--------|--------|---------:|---------------|--------|\n");
+        for r in &policies.results {
+            out.push_str(&format!(
+                "| {} | {} | {} | `{}` | {} |\n",
+                escape_cell(&r.name),
+                r.action.as_str(),
+                r.count,
+                r.condition,
+                if r.passed { "Passed" } else { "**Violated**" },
+            ));
+        }
+    }
+
+    if suppressed > 0 {
+        out.push_str(&format!(
+            "\n{} {} suppressed.\n",
+            suppressed,
+            plural(suppressed, "finding", "findings")
+        ));
+    }
+    out.push('\n');
+    out
+}
+
+/// Append `markdown` to the job summary file. Errors when
+/// `$GITHUB_STEP_SUMMARY` is unset (not running in GitHub Actions).
+pub fn append(markdown: &str) -> Result<PathBuf> {
+    let path = std::env::var_os(STEP_SUMMARY_ENV)
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .with_context(|| format!("${} is not set", STEP_SUMMARY_ENV))?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    file.write_all(markdown.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+fn severity_label(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "Error",
+        Severity::Warning => "Warning",
+        Severity::Info => "Info",
+    }
+}
+
+fn plural(count: usize, one: &'static str, many: &'static str) -> &'static str {
+    if count == 1 {
+        one
+    } else {
+        many
+    }
+}
+
+/// The headline, with the finding's details in a collapsible section
+fn message_cell(f: &Finding, locale: Locale) -> String {
+    let headline = escape_cell(&f.localized_message(locale));
+    let Some(details) = f.details.as_ref().filter(|d| !d.is_empty()) else {
+        return headline;
+    };
+    let lines: Vec<String> = details
+        .lines()
+        .iter()
+        .map(|line| escape_cell(&escape_html(line)).replace("    ", "&emsp;"))
+        .collect();
+    format!(
+        "{}<details><summary>Details</summary>{}</details>",
+        headline,
+        lines.join("<br>")
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Keep a message inside its table cell
+fn escape_cell(text: &str) -> String {
+    text.lines()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
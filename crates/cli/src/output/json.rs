@@ -2,60 +2,295 @@
 
 use serde::{Deserialize, Serialize};
 
-use revet_core::{BlastRadiusSummary, Finding, ReviewSummary, SuppressedFinding};
+use revet_core::ai::AiReport;
+use revet_core::analyzer::complexity::delta::DeltaSummary;
+use revet_core::analyzer::docs::DocCoverage;
+use revet_core::diff::StackChange;
+use revet_core::{
+    BlastRadiusSummary, BudgetReport, CodeKind, Confidence, CoverageReport, Degradation, Finding,
+    FindingDetails, Locale, MemoryReport, PolicyReport, Reproducibility, ReviewEstimate,
+    RiskReport, Severity, ShadowModule, TimingReport,
+};
 use std::path::Path;
-use std::time::Duration;
 
-use super::OutputFormatter;
+use super::{Out, Reporter, RunEnd, RunStart};
 
 // ── JSON document structs (kept public for tests) ─────────────────────────────
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct JsonOutput {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reproducibility: Option<Reproducibility>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blast_radius: Option<BlastRadiusSummary>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<RiskReport>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_estimate: Option<ReviewEstimate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai: Option<AiReport>,
+    /// The changes of a stack, oldest first (`revet diff --stack` only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<JsonChange>,
     pub findings: Vec<JsonFinding>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policies: Option<JsonPolicies>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<CoverageReport>,
     pub summary: JsonSummary,
 }
 
+/// One change of a stack and its finding counts
 #[derive(Debug, Serialize, Deserialize)]
+pub struct JsonChange {
+    pub commit: String,
+    /// The commit the change was diffed against
+    pub parent: String,
+    pub subject: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_id: Option<String>,
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+}
+
+impl JsonChange {
+    pub fn new(change: &StackChange) -> Self {
+        Self {
+            commit: change.commit.clone(),
+            parent: change.parent.clone(),
+            subject: change.subject.clone(),
+            change_id: change.change_id.clone(),
+            errors: 0,
+            warnings: 0,
+            info: 0,
+        }
+    }
+}
+
+/// `[[policy]]` results
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonPolicies {
+    /// Whether no `fail` policy was violated
+    pub passed: bool,
+    pub results: Vec<JsonPolicyResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonPolicyResult {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub filter: String,
+    /// `fail`, `warn` or `require-escalation`
+    pub action: String,
+    /// Violation condition, e.g. `count > 5` or `exists`
+    pub condition: String,
+    pub count: usize,
+    pub passed: bool,
+}
+
+impl JsonPolicies {
+    pub fn new(report: &PolicyReport) -> Self {
+        Self {
+            passed: !report.fails(),
+            results: report
+                .results
+                .iter()
+                .map(|r| JsonPolicyResult {
+                    name: r.name.clone(),
+                    description: r.description.clone(),
+                    filter: r.filter.clone(),
+                    action: r.action.as_str().to_string(),
+                    condition: r.condition.clone(),
+                    count: r.count,
+                    passed: r.passed,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(schemars::JsonSchema))]
 pub struct JsonFinding {
     pub id: String,
+    #[cfg_attr(feature = "api", schemars(extend("enum" = ["error", "warning", "info"])))]
     pub severity: String,
     pub message: String,
     pub file: String,
     pub line: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+    #[serde(default, skip_serializing_if = "Confidence::is_high")]
+    pub confidence: Confidence,
+    /// `message` in the selected locale; omitted for English
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized_message: Option<String>,
+    /// Workspace repository the finding belongs to (`revet multi` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    /// Change of the stack the finding belongs to: its `Change-Id`, else
+    /// its commit (`revet diff --stack` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change: Option<String>,
+    /// In a generated file (`[files] generated = "info-only"`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub generated: bool,
+    /// `test` or `script`; omitted for production code
+    #[serde(default, skip_serializing_if = "CodeKind::is_production")]
+    pub code_kind: CodeKind,
+    /// Why `--ai` did not enrich this eligible finding (`budget`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skipped_reason: Option<String>,
+    /// Namespaced rule of an imported finding (`EXT-semgrep-<ruleId>`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    /// External tool an imported finding came from (`revet import`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_tool: Option<String>,
+    /// Long-form content behind `message`: full query text, every
+    /// location, version tables
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<FindingDetails>,
+}
+
+impl JsonFinding {
+    /// `finding` as a JSON finding, with a `localized_message` in `locale`
+    /// (none for English)
+    pub fn new(finding: &Finding, locale: Locale) -> Self {
+        Self {
+            id: finding.id.clone(),
+            severity: finding.severity.to_string(),
+            message: finding.message.clone(),
+            file: finding.file.display().to_string(),
+            line: finding.line,
+            column: finding.column,
+            end_column: finding.end_column,
+            confidence: finding.confidence,
+            localized_message: (locale != Locale::En).then(|| finding.localized_message(locale)),
+            repo: None,
+            change: None,
+            generated: finding.generated,
+            code_kind: finding.code_kind,
+            skipped_reason: finding.ai_skipped.clone(),
+            rule: finding.source_tool.as_ref().and(finding.rule.clone()),
+            source_tool: finding.source_tool.clone(),
+            details: finding.details.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(schemars::JsonSchema))]
 pub struct JsonSummary {
     pub errors: usize,
     pub warnings: usize,
     pub info: usize,
+    /// Findings in generated files, not counted above
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub generated: usize,
+    /// Complexity findings reported and deferred in delta mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complexity_delta: Option<DeltaSummary>,
+    /// Documentation coverage overall, per project and per file, when
+    /// `DOC` ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_coverage: Option<DocCoverage>,
+    /// `budget_exceeded`, the budget and the skipped phases, with
+    /// `--time-budget`
+    #[serde(flatten)]
+    pub budget: Option<BudgetReport>,
+    /// Load shed to stay under `[performance] max_memory_mb`, and whether
+    /// the run stopped over it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemoryReport>,
+    /// Per-analyzer time and findings with `--timings`, plus the slowest
+    /// files with `--timings=full`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<TimingReport>,
+    /// Findings of modules in shadow mode (`rollout_percent`), not
+    /// reported or counted above
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shadow: Vec<ShadowModule>,
+    /// Findings `revet ignore` entries hid; present only in runs the
+    /// entries applied to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_ignored: Option<usize>,
+    /// Features that skipped objects a partial clone lacks, and why
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub degraded: Vec<Degradation>,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
 }
 
 // ── Formatter struct ─────────────────────────────────────────────────────────
 
 /// Accumulates findings in memory and serialises the whole JSON document on
-/// [`finalize`](OutputFormatter::finalize).
+/// [`finalize`](Reporter::finalize).
 pub struct JsonFormatter {
+    reproducibility: Option<Reproducibility>,
     blast_radius: Option<BlastRadiusSummary>,
+    risk: Option<RiskReport>,
+    review_estimate: Option<ReviewEstimate>,
+    ai: Option<AiReport>,
     findings: Vec<JsonFinding>,
+    policies: Option<JsonPolicies>,
+    coverage: Option<CoverageReport>,
     summary: JsonSummary,
+    locale: Locale,
+    current_repo: Option<String>,
+    changes: Vec<JsonChange>,
+    out: Out,
 }
 
 impl JsonFormatter {
     pub fn new() -> Self {
         Self {
+            reproducibility: None,
             blast_radius: None,
+            risk: None,
+            review_estimate: None,
+            ai: None,
             findings: Vec::new(),
+            policies: None,
+            coverage: None,
             summary: JsonSummary {
                 errors: 0,
                 warnings: 0,
                 info: 0,
+                generated: 0,
+                complexity_delta: None,
+                doc_coverage: None,
+                budget: None,
+                memory: None,
+                timings: None,
+                shadow: Vec::new(),
+                session_ignored: None,
+                degraded: Vec::new(),
             },
+            locale: Locale::En,
+            current_repo: None,
+            changes: Vec::new(),
+            out: Out::stdout(),
         }
     }
+
+    /// Add a `localized_message` in `locale` to every finding (none for English)
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Print to `out` instead of stdout
+    pub fn with_out(mut self, out: Out) -> Self {
+        self.out = out;
+        self
+    }
 }
 
 impl Default for JsonFormatter {
@@ -64,52 +299,112 @@ impl Default for JsonFormatter {
     }
 }
 
-impl OutputFormatter for JsonFormatter {
-    fn write_blast_radius(&mut self, summary: &BlastRadiusSummary) {
-        self.blast_radius = Some(summary.clone());
+impl Reporter for JsonFormatter {
+    fn begin_run(&mut self, run: &RunStart) {
+        self.reproducibility = run.reproducibility.cloned();
+        self.blast_radius = run.blast_radius.cloned();
+        self.risk = run.risk.cloned();
+        self.review_estimate = run.review_estimate.cloned();
+        self.ai = run.ai.cloned();
     }
 
-    fn write_finding(&mut self, finding: &Finding, _repo_path: &Path) {
+    fn begin_repo(&mut self, repo: &str, _repo_path: &Path) {
+        self.current_repo = Some(repo.to_string());
+    }
+
+    fn begin_change(&mut self, change: &StackChange) {
+        self.changes.push(JsonChange::new(change));
+    }
+
+    fn report_finding(&mut self, finding: &Finding, _repo_path: &Path) {
+        let change = self.changes.last_mut().map(|change| {
+            match finding.severity {
+                Severity::Error => change.errors += 1,
+                Severity::Warning => change.warnings += 1,
+                Severity::Info => change.info += 1,
+            }
+            change
+                .change_id
+                .clone()
+                .unwrap_or_else(|| change.commit.clone())
+        });
         self.findings.push(JsonFinding {
-            id: finding.id.clone(),
-            severity: finding.severity.to_string(),
-            message: finding.message.clone(),
-            file: finding.file.display().to_string(),
-            line: finding.line,
+            repo: self.current_repo.clone(),
+            change,
+            ..JsonFinding::new(finding, self.locale)
         });
     }
 
-    fn write_summary(
-        &mut self,
-        summary: &ReviewSummary,
-        _suppressed: &[SuppressedFinding],
-        _elapsed: Duration,
-        _run_id: Option<&str>,
-    ) {
-        self.summary = JsonSummary {
-            errors: summary.errors,
-            warnings: summary.warnings,
-            info: summary.info,
-        };
+    fn end_run(&mut self, end: &RunEnd) {
+        self.policies = end.policies.map(JsonPolicies::new);
+        self.coverage = end.coverage.cloned();
+        // Without files, findings stay empty and the summary zeroed —
+        // finalize still emits valid JSON
+        if let Some(summary) = end.summary {
+            self.summary = JsonSummary {
+                errors: summary.errors,
+                warnings: summary.warnings,
+                info: summary.info,
+                generated: summary.generated,
+                complexity_delta: summary.complexity_delta,
+                doc_coverage: summary.doc_coverage.clone(),
+                budget: summary.budget.clone(),
+                memory: summary.memory.clone(),
+                timings: summary.timings.clone(),
+                shadow: summary.shadow.clone(),
+                session_ignored: summary.session_ignored,
+                degraded: summary.degraded.clone(),
+            };
+        }
+    }
+
+    fn finalize(&mut self) {
+        match serde_json::to_string_pretty(&self.take_output()) {
+            Ok(json) => outln!(self.out, "{}", json),
+            Err(e) => eprintln!("Failed to serialize JSON: {}", e),
+        }
     }
 
-    fn write_no_files(&mut self, _elapsed: Duration) {
-        // Leave findings empty and summary zeroed — finalize will emit valid JSON.
+    fn document(&mut self) -> Option<serde_json::Value> {
+        match serde_json::to_value(self.take_output()) {
+            Ok(document) => Some(document),
+            Err(e) => {
+                eprintln!("Failed to serialize JSON: {}", e);
+                None
+            }
+        }
     }
+}
 
-    fn finalize(&mut self) {
-        let out = JsonOutput {
+impl JsonFormatter {
+    fn take_output(&mut self) -> JsonOutput {
+        JsonOutput {
+            reproducibility: self.reproducibility.take().map(|mut r| {
+                r.revet_version = self.out.version(&r.revet_version);
+                r
+            }),
             blast_radius: self.blast_radius.take(),
+            risk: self.risk.take(),
+            review_estimate: self.review_estimate.take(),
+            ai: self.ai.take(),
+            changes: std::mem::take(&mut self.changes),
             findings: std::mem::take(&mut self.findings),
+            policies: self.policies.take(),
+            coverage: self.coverage.take(),
             summary: JsonSummary {
                 errors: self.summary.errors,
                 warnings: self.summary.warnings,
                 info: self.summary.info,
+                generated: self.summary.generated,
+                complexity_delta: self.summary.complexity_delta,
+                doc_coverage: self.summary.doc_coverage.take(),
+                budget: self.summary.budget.take(),
+                memory: self.summary.memory.take(),
+                timings: self.summary.timings.take(),
+                shadow: std::mem::take(&mut self.summary.shadow),
+                session_ignored: self.summary.session_ignored,
+                degraded: std::mem::take(&mut self.summary.degraded),
             },
-        };
-        match serde_json::to_string_pretty(&out) {
-            Ok(json) => println!("{}", json),
-            Err(e) => eprintln!("Failed to serialize JSON: {}", e),
         }
     }
 }
@@ -0,0 +1,256 @@
+//! JUnit XML output formatter, for CI servers that render test reports
+//! (Jenkins, GitLab, Buildkite).
+//!
+//! Buffers findings and renders the whole document on
+//! [`finalize`](super::Reporter::finalize): one `<testsuite>` per analyzer
+//! prefix (`SEC`, `ML`, ...) and, in it, one `<testcase>` per file, both in
+//! the order they were first reported. Every error or warning is a
+//! `<failure>` of its file's testcase; info findings are listed in the
+//! testcase's `<system-out>` and leave it passed or, with
+//! [`InfoFindings::Skipped`], skipped. Repository-level findings make a
+//! `(repository)` testcase ahead of the files.
+//!
+//! See: <https://github.com/testmoapp/junitxml>
+
+use revet_core::analyzer::registry::prefix_of;
+use revet_core::{Finding, Severity};
+use std::path::{Path, PathBuf};
+
+use super::checkstyle::escape;
+use super::github::relative_path;
+use super::{Out, Reporter, RunEnd};
+
+/// Testcase name of repository-level findings
+pub const REPOSITORY_TESTCASE: &str = "(repository)";
+
+/// What a testcase with info findings and no other findings reports as
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InfoFindings {
+    /// A passed testcase
+    #[default]
+    Passed,
+    /// A skipped testcase
+    Skipped,
+}
+
+impl InfoFindings {
+    /// `[output.junit] info`; anything unknown is passed
+    pub fn from_config(name: &str) -> Self {
+        match name {
+            "skipped" => InfoFindings::Skipped,
+            _ => InfoFindings::Passed,
+        }
+    }
+}
+
+/// The findings of one file under one prefix
+struct TestCase {
+    name: String,
+    /// `<failure>` elements
+    failures: Vec<String>,
+    /// Info findings, one line each
+    info: Vec<String>,
+}
+
+struct TestSuite {
+    prefix: String,
+    cases: Vec<TestCase>,
+}
+
+pub struct JunitFormatter {
+    repo_path: PathBuf,
+    info: InfoFindings,
+    suites: Vec<TestSuite>,
+    out: Out,
+}
+
+impl JunitFormatter {
+    pub fn new(repo_path: PathBuf) -> Self {
+        Self {
+            repo_path,
+            info: InfoFindings::default(),
+            suites: Vec::new(),
+            out: Out::stdout(),
+        }
+    }
+
+    /// Report testcases with only info findings as `info`
+    pub fn with_info(mut self, info: InfoFindings) -> Self {
+        self.info = info;
+        self
+    }
+
+    /// Print to `out` instead of stdout
+    pub fn with_out(mut self, out: Out) -> Self {
+        self.out = out;
+        self
+    }
+
+    fn skipped(&self, case: &TestCase) -> bool {
+        self.info == InfoFindings::Skipped && case.failures.is_empty()
+    }
+
+    fn render(&self) -> String {
+        let count = |suite: &TestSuite| {
+            let failed = suite.cases.iter().filter(|c| !c.failures.is_empty());
+            let skipped = suite.cases.iter().filter(|c| self.skipped(c));
+            (suite.cases.len(), failed.count(), skipped.count())
+        };
+        let (tests, failures, skipped) = self
+            .suites
+            .iter()
+            .map(count)
+            .fold((0, 0, 0), |(t, f, s), (tests, failed, skipped)| {
+                (t + tests, f + failed, s + skipped)
+            });
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str(&format!(
+            "<testsuites name=\"revet\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\">\n",
+            tests, failures, skipped
+        ));
+        for suite in &self.suites {
+            let (tests, failures, skipped) = count(suite);
+            out.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\">\n",
+                escape(&suite.prefix),
+                tests,
+                failures,
+                skipped
+            ));
+            let repository = suite.cases.iter().filter(|c| c.name.is_empty());
+            let files = suite.cases.iter().filter(|c| !c.name.is_empty());
+            for case in repository.chain(files) {
+                let name = if case.name.is_empty() {
+                    REPOSITORY_TESTCASE
+                } else {
+                    &case.name
+                };
+                out.push_str(&format!(
+                    "    <testcase name=\"{}\" classname=\"revet.{}\">\n",
+                    escape(name),
+                    escape(&suite.prefix)
+                ));
+                for failure in &case.failures {
+                    out.push_str(&format!("      {}\n", failure));
+                }
+                if self.skipped(case) {
+                    out.push_str(&format!(
+                        "      <skipped message=\"{} info finding{}\"/>\n",
+                        case.info.len(),
+                        if case.info.len() == 1 { "" } else { "s" }
+                    ));
+                }
+                if !case.info.is_empty() {
+                    out.push_str(&format!(
+                        "      <system-out>{}</system-out>\n",
+                        cdata(&case.info.join("\n"))
+                    ));
+                }
+                out.push_str("    </testcase>\n");
+            }
+            out.push_str("  </testsuite>\n");
+        }
+        out.push_str("</testsuites>\n");
+        out
+    }
+}
+
+/// `file:line`, or `(repository)` for repository-level findings
+fn location(finding: &Finding, file: &str) -> String {
+    if finding.is_repository_level() {
+        REPOSITORY_TESTCASE.to_string()
+    } else {
+        format!("{}:{}", file, finding.line)
+    }
+}
+
+/// `<failure message=".." type="ID">` with the message, location and
+/// suggestion as its text
+pub fn format_failure(finding: &Finding, file: &str) -> String {
+    let severity = match finding.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    };
+    let mut text = format!(
+        "{} {}: {}\nat {}",
+        severity,
+        finding.id,
+        finding.message,
+        location(finding, file)
+    );
+    if let Some(suggestion) = &finding.suggestion {
+        text.push_str(&format!("\nSuggestion: {}", suggestion));
+    }
+    format!(
+        "<failure message=\"{}\" type=\"{}\">{}</failure>",
+        escape(&finding.message),
+        escape(&finding.id),
+        cdata(&text)
+    )
+}
+
+/// `text` as CDATA sections. A `]]>` in it would end the section, so it is
+/// split across two; characters XML 1.0 does not allow are dropped.
+pub(crate) fn cdata(text: &str) -> String {
+    let text: String = text
+        .chars()
+        .filter(|&c| (c as u32) >= 0x20 || matches!(c, '\n' | '\r' | '\t'))
+        .collect();
+    format!("<![CDATA[{}]]>", text.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+impl Reporter for JunitFormatter {
+    fn report_finding(&mut self, finding: &Finding, _repo_path: &Path) {
+        let file = if finding.is_repository_level() {
+            String::new()
+        } else {
+            relative_path(finding, &self.repo_path)
+        };
+        let prefix = prefix_of(&finding.id);
+        let suite = match self.suites.iter().position(|s| s.prefix == prefix) {
+            Some(i) => &mut self.suites[i],
+            None => {
+                self.suites.push(TestSuite {
+                    prefix: prefix.to_string(),
+                    cases: Vec::new(),
+                });
+                self.suites.last_mut().unwrap()
+            }
+        };
+        let case = match suite.cases.iter().position(|c| c.name == file) {
+            Some(i) => &mut suite.cases[i],
+            None => {
+                suite.cases.push(TestCase {
+                    name: file.clone(),
+                    failures: Vec::new(),
+                    info: Vec::new(),
+                });
+                suite.cases.last_mut().unwrap()
+            }
+        };
+        if finding.severity == Severity::Info {
+            case.info.push(format!(
+                "info {}: {} at {}",
+                finding.id,
+                finding.message,
+                location(finding, &file)
+            ));
+        } else {
+            case.failures.push(format_failure(finding, &file));
+        }
+    }
+
+    /// The report holds findings only; the summary and policies are left to
+    /// the terminal output
+    fn end_run(&mut self, _end: &RunEnd) {}
+
+    fn finalize(&mut self) {
+        self.out.text(&self.render());
+    }
+
+    fn text(&mut self) -> Option<String> {
+        Some(self.render())
+    }
+}
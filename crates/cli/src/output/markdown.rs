@@ -0,0 +1,144 @@
+//! Markdown output formatter — the job summary report (see [`job_summary`])
+//! as a format of its own, e.g. for a PR comment. In diff mode the review
+//! estimate heads the report, with the factors behind it.
+//!
+//! Buffers findings and renders the whole report on
+//! [`finalize`](super::Reporter::finalize).
+
+use revet_core::{Finding, Locale, PolicyReport, ReviewEstimate, ReviewSummary};
+use std::path::{Path, PathBuf};
+
+use super::job_summary;
+use super::{Out, Reporter, RunEnd, RunStart};
+
+pub struct MarkdownFormatter {
+    repo_path: PathBuf,
+    findings: Vec<Finding>,
+    summary: ReviewSummary,
+    suppressed: usize,
+    policies: PolicyReport,
+    passed: bool,
+    locale: Locale,
+    estimate: Option<ReviewEstimate>,
+    out: Out,
+}
+
+impl MarkdownFormatter {
+    pub fn new(repo_path: PathBuf) -> Self {
+        Self {
+            repo_path,
+            findings: Vec::new(),
+            summary: ReviewSummary::default(),
+            suppressed: 0,
+            policies: PolicyReport::default(),
+            passed: true,
+            locale: Locale::En,
+            estimate: None,
+            out: Out::stdout(),
+        }
+    }
+
+    /// Render finding messages in `locale`
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Print to `out` instead of stdout
+    pub fn with_out(mut self, out: Out) -> Self {
+        self.out = out;
+        self
+    }
+
+    fn render(&self) -> String {
+        let report = job_summary::render(
+            &self.findings,
+            &self.summary,
+            self.suppressed,
+            &self.policies,
+            &self.repo_path,
+            self.passed,
+            self.locale,
+        );
+        match (&self.estimate, report.split_once("\n\n")) {
+            (Some(estimate), Some((heading, rest))) => {
+                format!("{}\n\n{}\n{}", heading, estimate_block(estimate), rest)
+            }
+            _ => report,
+        }
+    }
+}
+
+/// The estimate's band and reviewers, then the factors it was summed from
+fn estimate_block(estimate: &ReviewEstimate) -> String {
+    let mut out = format!(
+        "**Review estimate: {}** (~{:.0} min, {} changed {})",
+        estimate.band,
+        estimate.minutes,
+        estimate.changed_lines,
+        if estimate.changed_lines == 1 {
+            "line"
+        } else {
+            "lines"
+        }
+    );
+    if !estimate.reviewers.is_empty() {
+        let reviewers: Vec<String> = estimate
+            .reviewers
+            .iter()
+            .map(|r| {
+                let owner = if r.owner { "owner, " } else { "" };
+                let files = if r.files == 1 { "file" } else { "files" };
+                format!("`{}` ({}{} {})", r.author, owner, r.files, files)
+            })
+            .collect();
+        out.push_str(&format!(" · Suggested reviewers: {}", reviewers.join(", ")));
+    }
+    out.push_str("\n\n<details><summary>How this was estimated</summary>\n\n");
+    out.push_str("| Factor | Value | Minutes each | Minutes |\n");
+    out.push_str("|--------|------:|-------------:|--------:|\n");
+    for factor in &estimate.factors {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            factor.name, factor.value, factor.weight, factor.minutes
+        ));
+    }
+    out.push_str(&format!("| **Total** | | | **{}** |\n\n", estimate.minutes));
+    if let [m, l, xl] = estimate.bands.as_slice() {
+        out.push_str(&format!(
+            "S below {} min, M below {}, L below {}, XL from {}. Weights are set under `[review_estimate]`.\n\n",
+            m, l, xl, xl
+        ));
+    }
+    out.push_str("</details>\n");
+    out
+}
+
+impl Reporter for MarkdownFormatter {
+    fn begin_run(&mut self, run: &RunStart) {
+        self.estimate = run.review_estimate.cloned();
+    }
+
+    fn report_finding(&mut self, finding: &Finding, _repo_path: &Path) {
+        self.findings.push(finding.clone());
+    }
+
+    fn end_run(&mut self, end: &RunEnd) {
+        if let Some(summary) = end.summary {
+            self.summary = summary.clone();
+        }
+        if let Some(report) = end.policies {
+            self.policies = report.clone();
+        }
+        self.suppressed = end.suppressed.len();
+        self.passed = end.passed;
+    }
+
+    fn finalize(&mut self) {
+        self.out.text(&self.render());
+    }
+
+    fn text(&mut self) -> Option<String> {
+        Some(self.render())
+    }
+}
@@ -1,95 +1,531 @@
-//! Output formatters for review findings.
+//! Output reporters for review findings.
 //!
-//! Every output format implements [`OutputFormatter`]. The caller drives it:
-//! 1. `write_finding` for each active finding
-//! 2. `write_suppressed` for each suppressed finding (only when `--show-suppressed`)
-//! 3. `write_summary` once with final stats
-//! 4. `finalize` to flush any buffered output (e.g. JSON serialises the whole
+//! Every output format implements [`Reporter`]. The caller drives it:
+//! 1. `begin_run` once with the profile, blast radius, risk and AI cost
+//!    (`revet multi` calls `begin_repo` before each repository's findings,
+//!    `revet diff --stack` `begin_change` before each change's)
+//! 2. `report_finding` for each active finding
+//! 3. `report_suppressed` for each suppressed finding (only when `--show-suppressed`)
+//! 4. `end_run` once with policies, coverage and the final stats
+//! 5. `finalize` to flush any buffered output (e.g. JSON serialises the whole
 //!    document at once)
+//!
+//! A run can write several formats at once (`--format sarif=report.sarif
+//! --format markdown=comment.md --format terminal`): a [`Pipeline`] passes
+//! every finding through each reporter in turn, and writes each report to
+//! its own destination. `--progressive` output is a reporter too, driven
+//! per partition while the review runs (see [`progressive`]).
+//!
+//! Reporters print through an [`Out`] (see [`sink`]), which the golden
+//! output tests point at a buffer.
 
+/// `println!` to an [`Out`]
+macro_rules! outln {
+    ($out:expr) => {
+        $out.line(format_args!(""))
+    };
+    ($out:expr, $($arg:tt)*) => {
+        $out.line(format_args!($($arg)*))
+    };
+}
+
+pub mod checkstyle;
+pub mod chunks;
+pub mod code_scanning;
 pub mod github;
 pub mod github_comment;
+pub mod github_matcher;
+pub mod job_summary;
 pub mod json;
+pub mod junit;
+pub mod markdown;
+pub mod progressive;
 pub mod sarif;
+pub mod sink;
 pub mod terminal;
 
-use revet_core::{BlastRadiusSummary, Finding, ReviewSummary, SuppressedFinding};
-use std::path::Path;
+use anyhow::Context;
+use colored::Colorize;
+use revet_core::ai::AiReport;
+use revet_core::analyzer::partition::Partition;
+use revet_core::diff::StackChange;
+use revet_core::{
+    BlastRadiusSummary, CoverageReport, Finding, PolicyReport, Reproducibility, ReviewEstimate,
+    ReviewSummary, RiskReport, SuppressedFinding,
+};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use crate::Cli;
-use revet_core::RevetConfig;
+use crate::exit;
+use crate::{Cli, OutputFormat};
+use revet_core::config::OutputConfig;
+use revet_core::{Locale, RevetConfig};
+pub use sink::Out;
 
 // ── Output format enum ────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Format {
     Terminal,
     Json,
     Sarif,
     Github,
+    GithubMatcher,
+    Markdown,
+    Checkstyle,
+    /// JUnit XML, with what info findings report as (`[output.junit] info`)
+    Junit(junit::InfoFindings),
+}
+
+impl Format {
+    /// Every review format, in `--format` help order
+    pub const ALL: [Format; 8] = [
+        Format::Terminal,
+        Format::Json,
+        Format::Sarif,
+        Format::Github,
+        Format::GithubMatcher,
+        Format::Markdown,
+        Format::Checkstyle,
+        Format::Junit(junit::InfoFindings::Passed),
+    ];
+
+    /// The review format of a `--format` value; `None` for formats of other
+    /// commands
+    fn from_flag(format: OutputFormat) -> Option<Self> {
+        match format {
+            OutputFormat::Terminal => Some(Format::Terminal),
+            OutputFormat::Json => Some(Format::Json),
+            OutputFormat::Sarif => Some(Format::Sarif),
+            OutputFormat::Github => Some(Format::Github),
+            OutputFormat::GithubMatcher => Some(Format::GithubMatcher),
+            OutputFormat::Markdown => Some(Format::Markdown),
+            OutputFormat::Checkstyle => Some(Format::Checkstyle),
+            OutputFormat::Junit => Some(Format::Junit(junit::InfoFindings::default())),
+            // SBOM and LSP formats only apply to `revet sbom` and `revet fixes`
+            OutputFormat::Cyclonedx | OutputFormat::SpdxJson | OutputFormat::LspJson => None,
+        }
+    }
+
+    /// `[output] format`; anything unknown is terminal
+    fn from_config(name: &str) -> Self {
+        match name {
+            "json" => Format::Json,
+            "sarif" => Format::Sarif,
+            "github" => Format::Github,
+            "github-matcher" => Format::GithubMatcher,
+            "markdown" => Format::Markdown,
+            "checkstyle" => Format::Checkstyle,
+            "junit" => Format::Junit(junit::InfoFindings::default()),
+            _ => Format::Terminal,
+        }
+    }
+
+    /// Name as `--format` spells it
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Terminal => "terminal",
+            Format::Json => "json",
+            Format::Sarif => "sarif",
+            Format::Github => "github",
+            Format::GithubMatcher => "github-matcher",
+            Format::Markdown => "markdown",
+            Format::Checkstyle => "checkstyle",
+            Format::Junit(_) => "junit",
+        }
+    }
+
+    /// Whether the report can go to a file; the others only print
+    pub fn writes_files(self) -> bool {
+        matches!(
+            self,
+            Format::Json | Format::Sarif | Format::Markdown | Format::Checkstyle | Format::Junit(_)
+        )
+    }
+}
+
+/// One report of a run: its format and the file it goes to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    pub format: Format,
+    /// `None` prints the report to stdout
+    pub output: Option<PathBuf>,
+}
+
+impl Target {
+    pub fn stdout(format: Format) -> Self {
+        Self {
+            format,
+            output: None,
+        }
+    }
+}
+
+/// The reports of a review run: every `--format FORMAT[=PATH]`, else
+/// `[output] format` (default terminal). `--output` is the path of a single
+/// format given without one.
+///
+/// Only JSON, SARIF, markdown, Checkstyle and JUnit go to files, and at most one report prints
+/// to stdout; anything else is a usage error.
+pub fn resolve_targets(cli: &Cli, config: &RevetConfig) -> anyhow::Result<Vec<Target>> {
+    let targets: Vec<Target> = cli
+        .format
+        .iter()
+        .filter_map(|arg| {
+            Format::from_flag(arg.format).map(|format| Target {
+                format,
+                output: arg.output.clone(),
+            })
+        })
+        .collect();
+    let default = Format::from_config(&config.output.format);
+    let mut targets = with_output(targets, default, cli.output.as_deref())?;
+    let info = junit::InfoFindings::from_config(&config.output.junit.info);
+    for target in &mut targets {
+        if let Format::Junit(junit_info) = &mut target.format {
+            *junit_info = info;
+        }
+    }
+    Ok(targets)
 }
 
-pub fn resolve_format(cli: &Cli, config: &RevetConfig) -> Format {
-    if let Some(ref f) = cli.format {
-        return match f {
-            crate::OutputFormat::Json => Format::Json,
-            crate::OutputFormat::Sarif => Format::Sarif,
-            crate::OutputFormat::Github => Format::Github,
-            crate::OutputFormat::Terminal => Format::Terminal,
-        };
+/// `targets` (or `default` when empty) with `--output` applied, checked
+pub fn with_output(
+    mut targets: Vec<Target>,
+    default: Format,
+    output: Option<&Path>,
+) -> anyhow::Result<Vec<Target>> {
+    if targets.is_empty() {
+        targets.push(Target::stdout(default));
     }
-    match config.output.format.as_str() {
-        "json" => Format::Json,
-        "sarif" => Format::Sarif,
-        "github" => Format::Github,
-        _ => Format::Terminal,
+    if let Some(path) = output {
+        match targets.as_mut_slice() {
+            [target] if target.output.is_none() => target.output = Some(path.to_path_buf()),
+            _ => return Err(exit::usage(
+                "--output is the file of a single --format; with several, use --format FORMAT=PATH",
+            )),
+        }
     }
+
+    let mut stdout = None;
+    for (i, target) in targets.iter().enumerate() {
+        match &target.output {
+            Some(_) if !target.format.writes_files() => {
+                return Err(exit::usage(format!(
+                    "--format {} prints to stdout; only json, sarif, markdown, checkstyle and junit reports are written to a file",
+                    target.format.name()
+                )))
+            }
+            Some(path) => {
+                if targets[..i].iter().any(|t| t.output.as_ref() == Some(path)) {
+                    return Err(exit::usage(format!(
+                        "{} is the destination of two --format reports",
+                        path.display()
+                    )));
+                }
+            }
+            None => {
+                if let Some(first) = stdout.replace(target.format) {
+                    return Err(exit::usage(format!(
+                        "--format {} and --format {} both print to stdout; give one a file with --format FORMAT=PATH",
+                        first.name(),
+                        target.format.name()
+                    )));
+                }
+            }
+        }
+    }
+    Ok(targets)
+}
+
+/// Language for finding messages: `REVET_LOCALE`, then `[output] locale`
+pub fn resolve_locale(config: &RevetConfig) -> Locale {
+    Locale::resolve(&config.output.locale)
 }
 
 // ── Trait ─────────────────────────────────────────────────────────────────────
 
-pub trait OutputFormatter {
-    /// Write the PR blast radius summary before any findings.
-    /// Only called in diff mode when symbol changes are detected.
-    /// Default: no-op (keeps existing formatters working without changes).
-    fn write_blast_radius(&mut self, _summary: &BlastRadiusSummary) {}
+/// What a run reports before its findings
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RunStart<'a> {
+    /// The profile and flag overrides the run was configured with
+    pub reproducibility: Option<&'a Reproducibility>,
+    /// PR blast radius, in diff mode when symbol changes are detected
+    pub blast_radius: Option<&'a BlastRadiusSummary>,
+    /// Fan-in weighted risk scores of the changed symbols, in diff mode
+    pub risk: Option<&'a RiskReport>,
+    /// Review effort and suggested reviewers, in diff mode; only JSON and
+    /// markdown show it
+    pub review_estimate: Option<&'a ReviewEstimate>,
+    /// AI cost estimate (and actual cost, unless `--estimate-only`), when
+    /// `--ai` ran
+    pub ai: Option<&'a AiReport>,
+}
 
-    /// Write one active finding.
-    fn write_finding(&mut self, finding: &Finding, repo_path: &Path);
+/// What a run reports after its findings
+#[derive(Debug, Clone, Copy)]
+pub struct RunEnd<'a> {
+    /// Final stats; `None` when no files were discovered
+    pub summary: Option<&'a ReviewSummary>,
+    pub suppressed: &'a [SuppressedFinding],
+    /// Which `[[policy]]` entries passed or were violated, when any are
+    /// configured
+    pub policies: Option<&'a PolicyReport>,
+    /// Which files each analyzer covered, with `--coverage-report`
+    pub coverage: Option<&'a CoverageReport>,
+    pub elapsed: Duration,
+    pub run_id: Option<&'a str>,
+    /// The run's gate / fail-on / policy result
+    pub passed: bool,
+}
 
-    /// Write one suppressed finding. Default: no-op (most formats ignore these).
-    fn write_suppressed(&mut self, _sf: &SuppressedFinding, _repo_path: &Path) {}
+impl<'a> RunEnd<'a> {
+    /// The end of a run that discovered no files
+    pub fn no_files(elapsed: Duration) -> Self {
+        Self {
+            summary: None,
+            suppressed: &[],
+            policies: None,
+            coverage: None,
+            elapsed,
+            run_id: None,
+            passed: true,
+        }
+    }
 
-    /// Write the final summary line(s) after all findings have been written.
-    fn write_summary(
-        &mut self,
-        summary: &ReviewSummary,
-        suppressed: &[SuppressedFinding],
-        elapsed: Duration,
-        run_id: Option<&str>,
-    );
+    /// The end of a run with `summary`
+    pub fn new(summary: &'a ReviewSummary, suppressed: &'a [SuppressedFinding]) -> Self {
+        Self {
+            summary: Some(summary),
+            suppressed,
+            ..Self::no_files(Duration::ZERO)
+        }
+    }
+}
+
+/// Renders a run's findings in one format. Reporters only read what they
+/// are given; findings are never modified on their way through.
+pub trait Reporter {
+    /// Start the run. Default: no-op.
+    fn begin_run(&mut self, _run: &RunStart) {}
+
+    /// Announce the directory partitions the per-file analyzers run in and
+    /// the analyzers whose findings only come at the end (`--progressive`
+    /// only). Default: no-op.
+    fn begin_partitions(&mut self, _partitions: usize, _pending: &[&str]) {}
 
-    /// Called instead of the normal flow when no files were discovered.
-    fn write_no_files(&mut self, elapsed: Duration);
+    /// The findings of a completed partition (`index` is 1-based), before
+    /// the review is done. They are reported again with
+    /// [`report_finding`](Self::report_finding). Default: no-op.
+    fn report_partition(&mut self, _index: usize, _partition: &Partition, _findings: &[Finding]) {}
 
-    /// Flush/finalize output. Called once, after `write_summary`.
-    /// Formatters that buffer (JSON, SARIF) emit their document here.
+    /// Start the findings of one workspace repository (`revet multi` only).
+    /// Default: no-op.
+    fn begin_repo(&mut self, _repo: &str, _repo_path: &Path) {}
+
+    /// Start the findings of one change of a stack (`revet diff --stack`
+    /// only). Default: no-op.
+    fn begin_change(&mut self, _change: &StackChange) {}
+
+    /// Report one active finding.
+    fn report_finding(&mut self, finding: &Finding, repo_path: &Path);
+
+    /// Report one suppressed finding. Default: no-op (most formats ignore
+    /// these).
+    fn report_suppressed(&mut self, _sf: &SuppressedFinding, _repo_path: &Path) {}
+
+    /// End the run: policies, coverage and the summary, after all findings.
+    fn end_run(&mut self, end: &RunEnd);
+
+    /// Flush/finalize output to stdout. Called once, after `end_run`.
+    /// Reporters that buffer (JSON, SARIF) emit their document here.
     fn finalize(&mut self) {}
+
+    /// The buffered document, for writing JSON or SARIF to a file instead of
+    /// [`finalize`](Self::finalize). `None` for other formats.
+    fn document(&mut self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// The rendered report, for writing a text format (markdown, Checkstyle,
+    /// JUnit) to a file
+    /// instead of [`finalize`](Self::finalize). `None` for other formats.
+    fn text(&mut self) -> Option<String> {
+        None
+    }
 }
 
 // ── Factory ───────────────────────────────────────────────────────────────────
 
-/// Create the right formatter for the requested format.
-pub fn make_formatter(
+/// Create the right reporter for the requested format.
+///
+/// Terminal and markdown output render messages in `locale`; JSON and SARIF
+/// keep English messages and add a `localized_message` for non-English
+/// locales. `verbose` has the terminal print each finding's details. The
+/// reporter prints to `out`.
+pub fn make_reporter(
     format: Format,
     repo_path: &Path,
     show_suppressed: bool,
-) -> Box<dyn OutputFormatter> {
+    verbose: bool,
+    locale: Locale,
+    out: Out,
+) -> Box<dyn Reporter> {
     match format {
-        Format::Terminal => Box::new(terminal::TerminalFormatter::new(show_suppressed)),
-        Format::Json => Box::new(json::JsonFormatter::new()),
-        Format::Sarif => Box::new(sarif::SarifFormatter::new(repo_path.to_path_buf())),
-        Format::Github => Box::new(github::GithubFormatter::new(repo_path.to_path_buf())),
+        Format::Terminal => Box::new(
+            terminal::TerminalFormatter::new(show_suppressed)
+                .with_locale(locale)
+                .with_details(verbose)
+                .with_out(out),
+        ),
+        Format::Json => Box::new(json::JsonFormatter::new().with_locale(locale).with_out(out)),
+        Format::Sarif => Box::new(
+            sarif::SarifFormatter::new(repo_path.to_path_buf())
+                .with_locale(locale)
+                .with_out(out),
+        ),
+        Format::Github => {
+            Box::new(github::GithubFormatter::new(repo_path.to_path_buf()).with_out(out))
+        }
+        Format::GithubMatcher => Box::new(
+            github_matcher::GithubMatcherFormatter::new(repo_path.to_path_buf()).with_out(out),
+        ),
+        Format::Markdown => Box::new(
+            markdown::MarkdownFormatter::new(repo_path.to_path_buf())
+                .with_locale(locale)
+                .with_out(out),
+        ),
+        Format::Checkstyle => {
+            Box::new(checkstyle::CheckstyleFormatter::new(repo_path.to_path_buf()).with_out(out))
+        }
+        Format::Junit(info) => Box::new(
+            junit::JunitFormatter::new(repo_path.to_path_buf())
+                .with_info(info)
+                .with_out(out),
+        ),
+    }
+}
+
+// ── Pipeline ──────────────────────────────────────────────────────────────────
+
+/// The reporters of one run, each with its destination. Every call is passed
+/// to each reporter in turn, so one pass over the findings feeds them all.
+pub struct Pipeline {
+    reporters: Vec<(Target, Box<dyn Reporter>)>,
+}
+
+impl Pipeline {
+    pub fn new(
+        targets: &[Target],
+        repo_path: &Path,
+        show_suppressed: bool,
+        verbose: bool,
+        locale: Locale,
+    ) -> Self {
+        let reporters = targets
+            .iter()
+            .map(|target| {
+                let reporter = make_reporter(
+                    target.format,
+                    repo_path,
+                    show_suppressed,
+                    verbose,
+                    locale,
+                    Out::stdout(),
+                );
+                (target.clone(), reporter)
+            })
+            .collect();
+        Self { reporters }
+    }
+
+    /// Finish every report: print the stdout one, write the others to their
+    /// files — JSON and SARIF in chunks with an index for a directory (see
+    /// [`chunks`]). Returns the JSON and SARIF files written, per format.
+    pub fn finish(
+        &mut self,
+        config: &OutputConfig,
+    ) -> anyhow::Result<Vec<(Format, chunks::Written)>> {
+        let mut written = Vec::new();
+        for (target, reporter) in &mut self.reporters {
+            let Some(path) = &target.output else {
+                reporter.finalize();
+                continue;
+            };
+            let kind = match target.format {
+                Format::Json => chunks::Kind::Json,
+                Format::Sarif => chunks::Kind::Sarif,
+                Format::Markdown | Format::Checkstyle | Format::Junit(_) => {
+                    let text = reporter.text().unwrap_or_default();
+                    std::fs::write(path, text)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    eprintln!("  {} {}", "Report:".bold(), path.display());
+                    continue;
+                }
+                other => {
+                    return Err(exit::usage(format!(
+                        "--format {} prints to stdout; only json, sarif, markdown, checkstyle and junit reports are written to a file",
+                        other.name()
+                    )))
+                }
+            };
+            let Some(document) = reporter.document() else {
+                anyhow::bail!("Failed to build the {} report", kind.extension());
+            };
+            let files = chunks::write(document, kind, path, kind.limits(config))?;
+            chunks::print_summary(&files, kind);
+            written.push((target.format, files));
+        }
+        Ok(written)
+    }
+}
+
+impl Reporter for Pipeline {
+    fn begin_run(&mut self, run: &RunStart) {
+        for (_, reporter) in &mut self.reporters {
+            reporter.begin_run(run);
+        }
+    }
+
+    fn begin_partitions(&mut self, partitions: usize, pending: &[&str]) {
+        for (_, reporter) in &mut self.reporters {
+            reporter.begin_partitions(partitions, pending);
+        }
+    }
+
+    fn report_partition(&mut self, index: usize, partition: &Partition, findings: &[Finding]) {
+        for (_, reporter) in &mut self.reporters {
+            reporter.report_partition(index, partition, findings);
+        }
+    }
+
+    fn begin_repo(&mut self, repo: &str, repo_path: &Path) {
+        for (_, reporter) in &mut self.reporters {
+            reporter.begin_repo(repo, repo_path);
+        }
+    }
+
+    fn begin_change(&mut self, change: &StackChange) {
+        for (_, reporter) in &mut self.reporters {
+            reporter.begin_change(change);
+        }
+    }
+
+    fn report_finding(&mut self, finding: &Finding, repo_path: &Path) {
+        for (_, reporter) in &mut self.reporters {
+            reporter.report_finding(finding, repo_path);
+        }
+    }
+
+    fn report_suppressed(&mut self, sf: &SuppressedFinding, repo_path: &Path) {
+        for (_, reporter) in &mut self.reporters {
+            reporter.report_suppressed(sf, repo_path);
+        }
+    }
+
+    fn end_run(&mut self, end: &RunEnd) {
+        for (_, reporter) in &mut self.reporters {
+            reporter.end_run(end);
+        }
     }
 }
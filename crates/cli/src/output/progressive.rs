@@ -0,0 +1,227 @@
+//! Progressive output (`--progressive github|ndjson`) for CI logs.
+//!
+//! Per-file analyzers run one directory partition at a time; as each
+//! partition completes, its findings — after the generated-file policy, the
+//! confidence floor, inline suppression, per-path rules and the baseline,
+//! with their final IDs — are written to stderr, while the normal output on
+//! stdout still waits for the whole review. Findings of whole-repository
+//! analyzers (graph analyzers, duplication, impact analysis, ...) follow in
+//! one final flush; the stream says up front which analyzers are pending.
+//!
+//! - `github`: `::group::` per partition holding its `::error`/`::warning`
+//!   annotations, opened by a `::notice` naming the pending analyzers
+//! - `ndjson`: one JSON object per line, `event` being `pending`, `finding`,
+//!   `partition` (a partition is complete) or `done`
+//!
+//! [`ProgressiveOutput`] is a [`Reporter`]: the review drives it with
+//! `begin_partitions` and `report_partition` while the analyzers run, then
+//! reports the final findings and ends the run like any other format.
+
+use revet_core::analyzer::partition::Partition;
+use revet_core::{Finding, Locale};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::github::{escape_data, format_finding};
+use super::json::JsonFinding;
+use super::{Reporter, RunEnd};
+use crate::ProgressiveFormat;
+
+/// Writes partition results as they complete and the rest at the end
+pub struct ProgressiveOutput {
+    format: ProgressiveFormat,
+    repo_path: PathBuf,
+    locale: Locale,
+    total: usize,
+    emitted: HashSet<(String, PathBuf, usize)>,
+    /// Final findings no partition reported, for the final flush
+    rest: Vec<Finding>,
+    /// Final findings reported
+    reported: usize,
+    out: Box<dyn Write + Send>,
+}
+
+/// One NDJSON line
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Event<'a> {
+    Pending {
+        partitions: usize,
+        analyzers: &'a [&'a str],
+    },
+    Finding {
+        /// 1-based partition, `null` in the final flush
+        partition: Option<usize>,
+        #[serde(flatten)]
+        finding: Box<JsonFinding>,
+    },
+    Partition {
+        index: usize,
+        total: usize,
+        dirs: &'a [String],
+        findings: usize,
+    },
+    Done {
+        findings: usize,
+        errors: usize,
+        warnings: usize,
+        info: usize,
+    },
+}
+
+impl ProgressiveOutput {
+    /// Progressive output on stderr
+    pub fn stderr(format: ProgressiveFormat, repo_path: &Path, locale: Locale) -> Self {
+        Self::new(format, repo_path, locale, Box::new(std::io::stderr()))
+    }
+
+    pub fn new(
+        format: ProgressiveFormat,
+        repo_path: &Path,
+        locale: Locale,
+        out: Box<dyn Write + Send>,
+    ) -> Self {
+        Self {
+            format,
+            repo_path: repo_path.to_path_buf(),
+            locale,
+            total: 0,
+            emitted: HashSet::new(),
+            rest: Vec::new(),
+            reported: 0,
+            out,
+        }
+    }
+}
+
+impl Reporter for ProgressiveOutput {
+    fn begin_partitions(&mut self, partitions: usize, pending: &[&str]) {
+        self.total = partitions;
+        match self.format {
+            ProgressiveFormat::Github => {
+                let pending = if pending.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        " Pending until the review finishes: {}.",
+                        pending.join(", ")
+                    )
+                };
+                self.line(format!(
+                    "::notice title=revet::Annotating findings per directory ({} partition(s)).{}",
+                    partitions,
+                    escape_data(&pending)
+                ));
+            }
+            ProgressiveFormat::Ndjson => self.event(&Event::Pending {
+                partitions,
+                analyzers: pending,
+            }),
+        }
+    }
+
+    fn report_partition(&mut self, index: usize, partition: &Partition, findings: &[Finding]) {
+        for finding in findings {
+            self.emitted.insert(key(finding));
+        }
+        match self.format {
+            ProgressiveFormat::Github => {
+                if findings.is_empty() {
+                    return;
+                }
+                self.line(format!(
+                    "::group::revet: {} ({}/{}, {} finding(s))",
+                    escape_data(&partition.label()),
+                    index,
+                    self.total,
+                    findings.len()
+                ));
+                self.annotations(findings);
+                self.line("::endgroup::".to_string());
+            }
+            ProgressiveFormat::Ndjson => {
+                for finding in findings {
+                    self.finding(Some(index), finding);
+                }
+                self.event(&Event::Partition {
+                    index,
+                    total: self.total,
+                    dirs: &partition.dirs,
+                    findings: findings.len(),
+                });
+            }
+        }
+    }
+
+    fn report_finding(&mut self, finding: &Finding, _repo_path: &Path) {
+        self.reported += 1;
+        if !self.emitted.contains(&key(finding)) {
+            self.rest.push(finding.clone());
+        }
+    }
+
+    /// Write the final findings no partition reported, then (NDJSON) the
+    /// totals
+    fn end_run(&mut self, end: &RunEnd) {
+        let rest = std::mem::take(&mut self.rest);
+        match self.format {
+            ProgressiveFormat::Github => {
+                if rest.is_empty() {
+                    return;
+                }
+                self.line(format!(
+                    "::group::revet: whole-repository findings ({} finding(s))",
+                    rest.len()
+                ));
+                self.annotations(&rest);
+                self.line("::endgroup::".to_string());
+            }
+            ProgressiveFormat::Ndjson => {
+                for finding in &rest {
+                    self.finding(None, finding);
+                }
+                let summary = end.summary.cloned().unwrap_or_default();
+                self.event(&Event::Done {
+                    findings: self.reported,
+                    errors: summary.errors,
+                    warnings: summary.warnings,
+                    info: summary.info,
+                });
+            }
+        }
+    }
+}
+
+impl ProgressiveOutput {
+    fn annotations<'a>(&mut self, findings: impl IntoIterator<Item = &'a Finding>) {
+        for finding in findings {
+            let line = format_finding(finding, &self.repo_path);
+            self.line(line);
+        }
+    }
+
+    fn finding(&mut self, partition: Option<usize>, finding: &Finding) {
+        let finding = Box::new(JsonFinding::new(finding, self.locale));
+        self.event(&Event::Finding { partition, finding });
+    }
+
+    fn event(&mut self, event: &Event) {
+        if let Ok(json) = serde_json::to_string(event) {
+            self.line(json);
+        }
+    }
+
+    /// Progressive output is best-effort: a closed stream does not fail
+    /// the review
+    fn line(&mut self, line: String) {
+        let _ = writeln!(self.out, "{}", line);
+        let _ = self.out.flush();
+    }
+}
+
+/// What identifies a finding across the partitions and the final list
+fn key(finding: &Finding) -> (String, PathBuf, usize) {
+    (finding.id.clone(), finding.file.clone(), finding.line)
+}
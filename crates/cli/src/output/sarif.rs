@@ -1,16 +1,19 @@
 //! SARIF 2.1.0 output formatter.
 //!
 //! Accumulates findings in memory and serialises the full SARIF document on
-//! [`finalize`](super::OutputFormatter::finalize).
+//! [`finalize`](super::Reporter::finalize).
 
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
 
-use revet_core::{Finding, ReviewSummary, Severity, SuppressedFinding};
+use revet_core::analyzer::registry::prefix_of;
+use revet_core::{
+    CodeKind, Finding, FindingDetails, FindingLocation, Locale, Reproducibility, Severity,
+};
 
-use super::OutputFormatter;
+use super::json::JsonPolicies;
+use super::{Out, Reporter, RunEnd, RunStart};
 
 // ── SARIF 2.1.0 structs ──────────────────────────────────────────────────────
 
@@ -28,6 +31,19 @@ pub struct SarifLog {
 pub struct SarifRun {
     pub tool: SarifTool,
     pub results: Vec<SarifResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<SarifRunProperties>,
+}
+
+/// Run-level property bag
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRunProperties {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reproducibility: Option<Reproducibility>,
+    /// `[[policy]]` results
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policies: Option<JsonPolicies>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -59,7 +75,34 @@ pub struct SarifResult {
     pub rule_index: usize,
     pub level: String,
     pub message: SarifMessage,
+    /// Empty for repository-level findings, which have no physical location
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub locations: Vec<SarifLocation>,
+    /// The repository root, for repository-level findings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analysis_target: Option<SarifArtifactLocation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<SarifResultProperties>,
+}
+
+/// Result-level property bag
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SarifResultProperties {
+    /// `message.text` in the selected locale (non-English locales only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized_message: Option<String>,
+    /// Workspace repository the result belongs to (`revet multi` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    /// `test` or `script`; omitted for production code
+    #[serde(default, skip_serializing_if = "CodeKind::is_production")]
+    pub code_kind: CodeKind,
+    /// External tool an imported result came from (`revet import`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_tool: Option<String>,
+    /// Long-form content behind `message.text` (see [`FindingDetails`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<FindingDetails>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -86,6 +129,10 @@ pub struct SarifArtifactLocation {
 #[serde(rename_all = "camelCase")]
 pub struct SarifRegion {
     pub start_line: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_column: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -115,7 +162,18 @@ fn rule_description(prefix: &str) -> &'static str {
         "DIMPORT" => "Dead import",
         "ENDPT" => "Hardcoded endpoint",
         "MAGIC" => "Magic number",
+        "RES" => "Resource leak or missing transaction",
+        "K8S" => "Kubernetes misconfiguration",
+        "SKEW" => "Version or config skew across packages",
+        "HOT" => "Frequently changed complex file",
+        "I18N" => "Missing or unused translation key",
+        "HIST" => "Secret in git history",
+        "SUPP" => "Suppression comment issue",
+        "CONF" => "Change to revet's own configuration",
         "COV" => "Missing test coverage",
+        "COUPLE" => "Feature envy or inappropriate intimacy",
+        "DOC" => "Public symbol without a documentation comment",
+        "RACE" => "Shared mutable state without synchronization",
         _ => "Code review finding",
     }
 }
@@ -129,7 +187,27 @@ fn severity_to_level(severity: &Severity) -> &'static str {
 }
 
 fn extract_prefix(id: &str) -> &str {
-    id.split('-').next().unwrap_or(id)
+    prefix_of(id)
+}
+
+/// SARIF rule of a finding: its ID prefix, or for imported findings the
+/// namespaced external rule (`EXT-semgrep-<ruleId>`)
+fn rule_id(f: &Finding) -> &str {
+    match (&f.source_tool, &f.rule) {
+        (Some(_), Some(rule)) => rule,
+        _ => extract_prefix(&f.id),
+    }
+}
+
+/// Short description of the rule [`rule_id`] names
+fn rule_text(f: &Finding) -> String {
+    match &f.source_tool {
+        Some(tool) => f
+            .rule_description
+            .clone()
+            .unwrap_or_else(|| format!("Reported by {}", tool)),
+        None => rule_description(extract_prefix(&f.id)).to_string(),
+    }
 }
 
 fn relative_uri(file: &Path, repo_path: &Path) -> String {
@@ -143,13 +221,28 @@ fn relative_uri(file: &Path, repo_path: &Path) -> String {
 // ── Public builder (kept for tests) ──────────────────────────────────────────
 
 pub fn build_sarif_log(findings: &[Finding], repo_path: &Path) -> SarifLog {
-    // Collect unique rule prefixes in stable order
-    let mut prefix_set: BTreeMap<String, &'static str> = BTreeMap::new();
-    for f in findings {
-        let prefix = extract_prefix(&f.id).to_string();
+    build_localized_sarif_log(findings, repo_path, Locale::En)
+}
+
+/// [`build_sarif_log`] with a `localized_message` property on each result
+/// when `locale` is not English
+pub fn build_localized_sarif_log(
+    findings: &[Finding],
+    repo_path: &Path,
+    locale: Locale,
+) -> SarifLog {
+    let tagged: Vec<(&Finding, Option<&str>)> = findings.iter().map(|f| (f, None)).collect();
+    build_log(&tagged, repo_path, locale)
+}
+
+/// Build the SARIF document from findings tagged with their workspace repo
+fn build_log(findings: &[(&Finding, Option<&str>)], repo_path: &Path, locale: Locale) -> SarifLog {
+    // Collect unique rules in stable order
+    let mut prefix_set: BTreeMap<String, String> = BTreeMap::new();
+    for (f, _) in findings {
         prefix_set
-            .entry(prefix.clone())
-            .or_insert_with(|| rule_description(&prefix));
+            .entry(rule_id(f).to_string())
+            .or_insert_with(|| rule_text(f));
     }
 
     let rules: Vec<SarifReportingDescriptor> = prefix_set
@@ -170,9 +263,8 @@ pub fn build_sarif_log(findings: &[Finding], repo_path: &Path) -> SarifLog {
 
     let results: Vec<SarifResult> = findings
         .iter()
-        .filter(|f| !f.file.as_os_str().is_empty())
-        .map(|f| {
-            let prefix = extract_prefix(&f.id);
+        .map(|&(f, repo)| {
+            let prefix = rule_id(f);
             let rule_index = prefix_index.get(prefix).copied().unwrap_or(0);
 
             SarifResult {
@@ -182,17 +274,38 @@ pub fn build_sarif_log(findings: &[Finding], repo_path: &Path) -> SarifLog {
                 message: SarifMessage {
                     text: f.message.clone(),
                 },
-                locations: vec![SarifLocation {
-                    physical_location: SarifPhysicalLocation {
-                        artifact_location: SarifArtifactLocation {
-                            uri: relative_uri(&f.file, repo_path),
-                            uri_base_id: "%SRCROOT%".to_string(),
+                locations: match f.location() {
+                    FindingLocation::File { path, line, column } => vec![SarifLocation {
+                        physical_location: SarifPhysicalLocation {
+                            artifact_location: SarifArtifactLocation {
+                                uri: relative_uri(path, repo_path),
+                                uri_base_id: "%SRCROOT%".to_string(),
+                            },
+                            region: SarifRegion {
+                                start_line: line.max(1),
+                                start_column: column,
+                                end_column: f.end_column,
+                            },
                         },
-                        region: SarifRegion {
-                            start_line: f.line.max(1),
-                        },
-                    },
-                }],
+                    }],
+                    FindingLocation::Repository => Vec::new(),
+                },
+                analysis_target: f.is_repository_level().then(|| SarifArtifactLocation {
+                    uri: ".".to_string(),
+                    uri_base_id: "%SRCROOT%".to_string(),
+                }),
+                properties: (locale != Locale::En
+                    || repo.is_some()
+                    || !f.code_kind.is_production()
+                    || f.source_tool.is_some()
+                    || f.details.is_some())
+                .then(|| SarifResultProperties {
+                    localized_message: (locale != Locale::En).then(|| f.localized_message(locale)),
+                    repo: repo.map(str::to_string),
+                    code_kind: f.code_kind,
+                    source_tool: f.source_tool.clone(),
+                    details: f.details.clone(),
+                }),
             }
         })
         .collect();
@@ -210,6 +323,7 @@ pub fn build_sarif_log(findings: &[Finding], repo_path: &Path) -> SarifLog {
                 },
             },
             results,
+            properties: None,
         }],
     }
 }
@@ -217,43 +331,100 @@ pub fn build_sarif_log(findings: &[Finding], repo_path: &Path) -> SarifLog {
 // ── Formatter struct ─────────────────────────────────────────────────────────
 
 pub struct SarifFormatter {
-    findings: Vec<Finding>,
+    findings: Vec<(Finding, Option<String>)>,
+    current_repo: Option<String>,
     repo_path: PathBuf,
+    reproducibility: Option<Reproducibility>,
+    policies: Option<JsonPolicies>,
+    locale: Locale,
+    out: Out,
 }
 
 impl SarifFormatter {
     pub fn new(repo_path: PathBuf) -> Self {
         Self {
             findings: Vec::new(),
+            current_repo: None,
             repo_path,
+            reproducibility: None,
+            policies: None,
+            locale: Locale::En,
+            out: Out::stdout(),
         }
     }
+
+    /// Add a `localized_message` property in `locale` to every result
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Print to `out` instead of stdout
+    pub fn with_out(mut self, out: Out) -> Self {
+        self.out = out;
+        self
+    }
 }
 
-impl OutputFormatter for SarifFormatter {
-    fn write_finding(&mut self, finding: &Finding, _repo_path: &Path) {
-        self.findings.push(finding.clone());
+impl Reporter for SarifFormatter {
+    fn begin_run(&mut self, run: &RunStart) {
+        self.reproducibility = run.reproducibility.cloned();
+    }
+
+    fn begin_repo(&mut self, repo: &str, _repo_path: &Path) {
+        self.current_repo = Some(repo.to_string());
     }
 
-    fn write_summary(
-        &mut self,
-        _summary: &ReviewSummary,
-        _suppressed: &[SuppressedFinding],
-        _elapsed: Duration,
-        _run_id: Option<&str>,
-    ) {
-        // SARIF has no summary section — nothing to do.
+    fn report_finding(&mut self, finding: &Finding, _repo_path: &Path) {
+        self.findings
+            .push((finding.clone(), self.current_repo.clone()));
     }
 
-    fn write_no_files(&mut self, _elapsed: Duration) {
-        // Empty findings list — finalize will emit a valid empty SARIF document.
+    fn end_run(&mut self, end: &RunEnd) {
+        // SARIF has no summary section; without files, finalize emits a
+        // valid empty document
+        self.policies = end.policies.map(JsonPolicies::new);
     }
 
     fn finalize(&mut self) {
-        let log = build_sarif_log(&self.findings, &self.repo_path);
-        match serde_json::to_string_pretty(&log) {
-            Ok(json) => println!("{}", json),
+        match serde_json::to_string_pretty(&self.take_log()) {
+            Ok(json) => outln!(self.out, "{}", json),
             Err(e) => eprintln!("Failed to serialize SARIF: {}", e),
         }
     }
+
+    fn document(&mut self) -> Option<serde_json::Value> {
+        match serde_json::to_value(self.take_log()) {
+            Ok(document) => Some(document),
+            Err(e) => {
+                eprintln!("Failed to serialize SARIF: {}", e);
+                None
+            }
+        }
+    }
+}
+
+impl SarifFormatter {
+    fn take_log(&mut self) -> SarifLog {
+        let tagged: Vec<(&Finding, Option<&str>)> = self
+            .findings
+            .iter()
+            .map(|(f, repo)| (f, repo.as_deref()))
+            .collect();
+        let mut log = build_log(&tagged, &self.repo_path, self.locale);
+        let driver = &mut log.runs[0].tool.driver;
+        driver.semantic_version = self.out.version(&driver.semantic_version);
+        let reproducibility = self.reproducibility.take().map(|mut r| {
+            r.revet_version = self.out.version(&r.revet_version);
+            r
+        });
+        let policies = self.policies.take();
+        if reproducibility.is_some() || policies.is_some() {
+            log.runs[0].properties = Some(SarifRunProperties {
+                reproducibility,
+                policies,
+            });
+        }
+        log
+    }
 }
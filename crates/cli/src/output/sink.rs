@@ -0,0 +1,104 @@
+//! Where reporters print, and how they print the fields that differ between
+//! two runs over the same findings.
+//!
+//! Every reporter writes through an [`Out`]: stdout by default, or a buffer
+//! to read the report back from. Elapsed time, run IDs and the revet
+//! version go through it too, so a [`redacted`](Out::redacted) report
+//! prints placeholders for them and can be compared byte for byte — the
+//! golden output tests (`crates/cli/tests/test_golden_output.rs`) render
+//! every format this way.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Printed for the elapsed time of a redacted report
+pub const ELAPSED: &str = "<elapsed>";
+
+/// Printed for run IDs in a redacted report
+pub const RUN_ID: &str = "<run-id>";
+
+/// Printed for the revet version in a redacted report
+pub const VERSION: &str = "<version>";
+
+/// A reporter's output: stdout, or a shared buffer
+#[derive(Debug, Clone, Default)]
+pub struct Out {
+    /// `None` prints to stdout
+    buffer: Option<Rc<RefCell<String>>>,
+    redact: bool,
+}
+
+impl Out {
+    pub fn stdout() -> Self {
+        Self::default()
+    }
+
+    /// Collect the output instead of printing it; clones share the buffer,
+    /// so keep one to read [`contents`](Self::contents) from
+    pub fn buffer() -> Self {
+        Self {
+            buffer: Some(Rc::default()),
+            redact: false,
+        }
+    }
+
+    /// Print placeholders for elapsed time, run IDs and the revet version
+    pub fn redacted(mut self) -> Self {
+        self.redact = true;
+        self
+    }
+
+    /// Everything written so far (empty for stdout)
+    pub fn contents(&self) -> String {
+        self.buffer
+            .as_ref()
+            .map(|b| b.borrow().clone())
+            .unwrap_or_default()
+    }
+
+    /// `println!`, to stdout or the buffer
+    pub fn line(&self, args: fmt::Arguments) {
+        match &self.buffer {
+            None => println!("{}", args),
+            Some(buffer) => {
+                let mut buffer = buffer.borrow_mut();
+                fmt::Write::write_fmt(&mut *buffer, args).ok();
+                buffer.push('\n');
+            }
+        }
+    }
+
+    /// `print!`, to stdout or the buffer
+    pub fn text(&self, text: &str) {
+        match &self.buffer {
+            None => print!("{}", text),
+            Some(buffer) => buffer.borrow_mut().push_str(text),
+        }
+    }
+
+    /// `1.2s`
+    pub fn elapsed(&self, elapsed: Duration) -> String {
+        if self.redact {
+            return ELAPSED.to_string();
+        }
+        format!("{:.1}s", elapsed.as_secs_f64())
+    }
+
+    pub fn run_id(&self, id: &str) -> String {
+        if self.redact {
+            return RUN_ID.to_string();
+        }
+        id.to_string()
+    }
+
+    /// `version`, the revet version a report or its reproducibility record
+    /// was written by
+    pub fn version(&self, version: &str) -> String {
+        if self.redact {
+            return VERSION.to_string();
+        }
+        version.to_string()
+    }
+}
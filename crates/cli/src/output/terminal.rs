@@ -7,21 +7,40 @@
 //!   |  Function signature changed — 3 dependent(s) affected
 //!   |  Fix: update all call sites
 //! ```
+//!
+//! A suggestion's code, when it has any, follows the `Fix:` line indented.
+//! Repository-level findings are held back and printed together under a
+//! `Repository` header after the others.
 
 use colored::Colorize;
+use revet_core::ai::AiReport;
+use revet_core::analyzer::registry::prefix_of;
+use revet_core::diff::StackChange;
+use revet_core::policy::Action;
 use revet_core::{
-    BlastRadiusSummary, Finding, ReviewSummary, RiskLevel, Severity, SuppressedFinding,
+    BlastRadiusSummary, CoverageReport, Finding, Locale, PolicyReport, Reproducibility,
+    ReviewSummary, RiskLevel, RiskReport, Severity, SuppressedFinding,
 };
 use std::path::Path;
 use std::time::Duration;
 
-use super::OutputFormatter;
+use super::{Out, Reporter, RunEnd, RunStart};
+
+/// Changed files listed under the risk banner
+const RISK_FILES_SHOWN: usize = 5;
 
 // ── Formatter struct ─────────────────────────────────────────────────────────
 
 pub struct TerminalFormatter {
     show_suppressed: bool,
     printed: usize, // total blocks printed so far (for blank-line spacing)
+    /// Repository-level findings, printed by [`Self::write_repository`]
+    repository: Vec<Finding>,
+    reproducibility: Option<Reproducibility>,
+    locale: Locale,
+    /// Print each finding's details under its headline (`--verbose`)
+    details: bool,
+    out: Out,
 }
 
 impl TerminalFormatter {
@@ -29,8 +48,31 @@ impl TerminalFormatter {
         Self {
             show_suppressed,
             printed: 0,
+            repository: Vec::new(),
+            reproducibility: None,
+            locale: Locale::En,
+            details: false,
+            out: Out::stdout(),
         }
     }
+
+    /// Render finding messages and suggestions in `locale`
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Print each finding's [`details`](Finding::details) under its headline
+    pub fn with_details(mut self, details: bool) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// Print to `out` instead of stdout
+    pub fn with_out(mut self, out: Out) -> Self {
+        self.out = out;
+        self
+    }
 }
 
 impl Default for TerminalFormatter {
@@ -39,17 +81,114 @@ impl Default for TerminalFormatter {
     }
 }
 
-// ── OutputFormatter impl ─────────────────────────────────────────────────────
+// ── Reporter impl ────────────────────────────────────────────────────────────
+
+impl Reporter for TerminalFormatter {
+    fn begin_run(&mut self, run: &RunStart) {
+        self.reproducibility = run.reproducibility.cloned();
+        if let Some(summary) = run.blast_radius {
+            self.write_blast_radius(summary);
+        }
+        if let Some(risk) = run.risk {
+            self.write_risk(risk);
+        }
+        if let Some(report) = run.ai {
+            self.write_ai(report);
+        }
+    }
+
+    fn begin_repo(&mut self, repo: &str, _repo_path: &Path) {
+        self.write_repository();
+        if self.printed > 0 {
+            outln!(self.out);
+        }
+        // Spacing restarts under the header
+        self.printed = 0;
+        outln!(self.out, "  {} {}", "\u{25b8}".cyan(), repo.bold());
+        outln!(self.out);
+    }
+
+    fn begin_change(&mut self, change: &StackChange) {
+        self.begin_repo(&change.label(), Path::new(""));
+    }
+
+    fn report_finding(&mut self, finding: &Finding, repo_path: &Path) {
+        if finding.is_repository_level() {
+            self.repository.push(finding.clone());
+            return;
+        }
+        if self.printed > 0 {
+            outln!(self.out);
+        }
+        self.printed += 1;
+        outln!(
+            self.out,
+            "{}",
+            finding_block(finding, repo_path, self.locale, self.details)
+        );
+    }
+
+    fn report_suppressed(&mut self, sf: &SuppressedFinding, repo_path: &Path) {
+        if !self.show_suppressed {
+            return;
+        }
+        if self.printed > 0 {
+            outln!(self.out);
+        }
+        self.printed += 1;
+        outln!(self.out, "{}", suppressed_block(sf, repo_path, self.locale));
+    }
+
+    fn end_run(&mut self, end: &RunEnd) {
+        self.write_repository();
+        if let Some(report) = end.policies {
+            self.write_policies(report);
+        }
+        if let Some(coverage) = end.coverage {
+            self.write_coverage(coverage);
+        }
+        match end.summary {
+            Some(summary) => self.write_summary(summary, end.suppressed, end.elapsed, end.run_id),
+            None => self.write_no_files(end.elapsed),
+        }
+    }
+}
+
+// ── Sections ─────────────────────────────────────────────────────────────────
+
+impl TerminalFormatter {
+    /// Print the held-back repository-level findings under their header
+    fn write_repository(&mut self) {
+        if self.repository.is_empty() {
+            return;
+        }
+        if self.printed > 0 {
+            outln!(self.out);
+        }
+        outln!(self.out, "  {}", "Repository".bold());
+        outln!(self.out, "  {}", "\u{2500}".repeat(60).dimmed());
+        for (i, finding) in std::mem::take(&mut self.repository).iter().enumerate() {
+            if i > 0 {
+                outln!(self.out);
+            }
+            self.printed += 1;
+            outln!(
+                self.out,
+                "{}",
+                finding_block(finding, Path::new(""), self.locale, self.details)
+            );
+        }
+    }
 
-impl OutputFormatter for TerminalFormatter {
     fn write_blast_radius(&mut self, summary: &BlastRadiusSummary) {
         if summary.directly_modified == 0 {
             return;
         }
         let line = "\u{2500}".repeat(60);
-        println!("  {}", "PR Blast Radius".bold());
-        println!("  {}", line.dimmed());
-        println!(
+        outln!(self.out, "  {}", "PR Blast Radius".bold());
+        outln!(self.out, "  {}", line.dimmed());
+        outln!(
+            self.out,
             "  {:<32} {}",
             "Directly modified symbols:".dimmed(),
             summary.directly_modified
@@ -67,7 +206,8 @@ impl OutputFormatter for TerminalFormatter {
         } else {
             String::new()
         };
-        println!(
+        outln!(
+            self.out,
             "  {:<32} {}{}",
             "Transitively affected callers:".dimmed(),
             summary.transitively_affected,
@@ -78,28 +218,203 @@ impl OutputFormatter for TerminalFormatter {
             RiskLevel::Medium => "MEDIUM".yellow().bold().to_string(),
             RiskLevel::High => "HIGH".red().bold().to_string(),
         };
-        println!("  {:<32} {}", "Risk:".dimmed(), risk_str);
-        println!("  {}", line.dimmed());
-        println!();
+        outln!(self.out, "  {:<32} {}", "Risk:".dimmed(), risk_str);
+        outln!(self.out, "  {}", line.dimmed());
+        outln!(self.out);
     }
 
-    fn write_finding(&mut self, finding: &Finding, repo_path: &Path) {
-        if self.printed > 0 {
-            println!();
+    fn write_risk(&mut self, risk: &RiskReport) {
+        if risk.symbols.is_empty() {
+            return;
         }
-        self.printed += 1;
-        println!("{}", finding_block(finding, repo_path));
+        let headline = risk.headline();
+        let headline = match risk.level {
+            RiskLevel::Low => headline.green(),
+            RiskLevel::Medium => headline.yellow(),
+            RiskLevel::High => headline.red(),
+        };
+        outln!(self.out, "  {}", headline.bold());
+        for file in risk.files.iter().take(RISK_FILES_SHOWN) {
+            let more = if file.symbols > 1 {
+                format!(" (+{} more)", file.symbols - 1)
+            } else {
+                String::new()
+            };
+            outln!(
+                self.out,
+                "    {:>3}  {:<40} {}{}",
+                file.score,
+                file.file.display(),
+                file.top_symbol.dimmed(),
+                more.dimmed()
+            );
+        }
+        if risk.files.len() > RISK_FILES_SHOWN {
+            outln!(
+                self.out,
+                "    {}",
+                format!("… {} more file(s)", risk.files.len() - RISK_FILES_SHOWN).dimmed()
+            );
+        }
+        outln!(self.out);
     }
 
-    fn write_suppressed(&mut self, sf: &SuppressedFinding, repo_path: &Path) {
-        if !self.show_suppressed {
+    fn write_ai(&mut self, report: &AiReport) {
+        // A real run reports its cost on the progress line
+        if report.actual_cost.is_some() {
             return;
         }
+        let estimate = &report.estimate;
+        let line = "\u{2500}".repeat(60);
+        outln!(
+            self.out,
+            "  {}",
+            "AI cost estimate (no API calls made)".bold()
+        );
+        outln!(self.out, "  {}", line.dimmed());
+        outln!(
+            self.out,
+            "  {:<32} {}",
+            "Eligible findings:".dimmed(),
+            estimate.eligible
+        );
+        outln!(
+            self.out,
+            "  {:<32} {}",
+            "Requests:".dimmed(),
+            estimate.requests
+        );
+        for (i, batch) in estimate.batches.iter().enumerate() {
+            outln!(
+                self.out,
+                "    {:>3}  {:>4} finding(s)  ~{} in / ~{} out tokens",
+                i + 1,
+                batch.findings,
+                batch.input_tokens,
+                batch.output_tokens
+            );
+        }
+        for provider in &estimate.providers {
+            outln!(
+                self.out,
+                "  {:<32} ~${:.4}",
+                format!("{} ({}):", provider.provider, provider.model).dimmed(),
+                provider.cost_usd
+            );
+        }
+        let budget = if estimate.exceeds_max_cost {
+            format!(
+                "{} finding(s) skipped for budget{}",
+                estimate.skipped_budget,
+                estimate
+                    .score_cutoff
+                    .map(|s| format!(", cutoff score {}", s))
+                    .unwrap_or_default()
+            )
+            .yellow()
+            .bold()
+            .to_string()
+        } else {
+            "within budget".green().to_string()
+        };
+        outln!(
+            self.out,
+            "  {:<32} ${:.4}  {}",
+            "Max cost:".dimmed(),
+            estimate.max_cost,
+            budget
+        );
+        outln!(self.out, "  {}", line.dimmed());
+        outln!(self.out);
+    }
+
+    fn write_policies(&mut self, report: &PolicyReport) {
         if self.printed > 0 {
-            println!();
+            outln!(self.out);
         }
         self.printed += 1;
-        println!("{}", suppressed_block(sf, repo_path));
+
+        outln!(self.out, "  {}", "Policies".bold());
+        for result in &report.results {
+            let mark = match (result.passed, result.action) {
+                (true, _) => "\u{2713}".green(),
+                (false, Action::Fail) => "\u{2717}".red(),
+                (false, _) => "!".yellow(),
+            };
+            let outcome = if result.passed {
+                "passed".normal()
+            } else {
+                match result.action {
+                    Action::Fail => "failed".red().bold(),
+                    Action::Warn => "warning".yellow(),
+                    Action::RequireEscalation => "requires escalation".yellow().bold(),
+                }
+            };
+            outln!(
+                self.out,
+                "  {} {:<32} {}  {}",
+                mark,
+                result.name,
+                outcome,
+                format!(
+                    "{} matching, violated when {}",
+                    result.count, result.condition
+                )
+                .dimmed()
+            );
+            if !result.passed {
+                if let Some(description) = &result.description {
+                    outln!(self.out, "      {}", description.dimmed());
+                }
+            }
+        }
+        if report.results.iter().any(|r| !r.passed) {
+            outln!(
+                self.out,
+                "  {}",
+                "Run with --explain-policy <name> to list the matching findings".dimmed()
+            );
+        }
+    }
+
+    fn write_coverage(&mut self, coverage: &CoverageReport) {
+        if self.printed > 0 {
+            outln!(self.out);
+        }
+        self.printed += 1;
+
+        outln!(self.out, "  {}", "Coverage".bold());
+        for file in &coverage.files {
+            let (mark, detail) = match file.skipped {
+                None => ("\u{2713}".green(), file.analyzers.join(", ").normal()),
+                Some(reason) => {
+                    let detail = match &file.parse_error {
+                        Some(e) => format!("{}: {}", reason, e),
+                        None => reason.to_string(),
+                    };
+                    ("\u{2013}".yellow(), detail.dimmed())
+                }
+            };
+            outln!(
+                self.out,
+                "  {} {:<48} {}",
+                mark,
+                file.path.display(),
+                detail
+            );
+        }
+        for group in &coverage.groups {
+            let label = group.directory.join(format!("*{}", group.extension));
+            let mut detail = format!("{}/{} analyzed", group.analyzed, group.files);
+            for (reason, count) in &group.skipped {
+                detail.push_str(&format!(", {} {}", count, reason));
+            }
+            outln!(self.out, "  {:<50} {}", label.display(), detail.dimmed());
+        }
+        outln!(self.out, "  {}", coverage.summary.to_string().dimmed());
+        for degraded in &coverage.degraded {
+            outln!(self.out, "  {}", format!("Degraded {}", degraded).yellow());
+        }
     }
 
     fn write_summary(
@@ -110,10 +425,10 @@ impl OutputFormatter for TerminalFormatter {
         run_id: Option<&str>,
     ) {
         if self.printed > 0 {
-            println!();
+            outln!(self.out);
         }
 
-        println!("  {}", "\u{2500}".repeat(60).dimmed());
+        outln!(self.out, "  {}", "\u{2500}".repeat(60).dimmed());
 
         // Error / warning / info counts
         let errors_str = if summary.errors > 0 {
@@ -150,16 +465,37 @@ impl OutputFormatter for TerminalFormatter {
 
         let info_str = format!("{} info", summary.info).dimmed().to_string();
 
-        println!(
-            "  {} \u{00b7} {} \u{00b7} {}",
-            errors_str, warnings_str, info_str
-        );
+        if summary.generated > 0 {
+            let generated_str = format!("{} in generated files", summary.generated)
+                .dimmed()
+                .to_string();
+            outln!(
+                self.out,
+                "  {} \u{00b7} {} \u{00b7} {} \u{00b7} {}",
+                errors_str,
+                warnings_str,
+                info_str,
+                generated_str
+            );
+        } else {
+            outln!(
+                self.out,
+                "  {} \u{00b7} {} \u{00b7} {}",
+                errors_str,
+                warnings_str,
+                info_str
+            );
+        }
 
         // Technical debt estimate
         let debt = summary.total_debt_minutes();
         if debt > 0 {
             let debt_str = format_debt(debt);
-            println!("  {}", format!("Technical debt: {}", debt_str).dimmed());
+            outln!(
+                self.out,
+                "  {}",
+                format!("Technical debt: {}", debt_str).dimmed()
+            );
         }
 
         // Suppression breakdown
@@ -181,8 +517,12 @@ impl OutputFormatter for TerminalFormatter {
             if per_path > 0 {
                 parts.push(format!("{} per-path", per_path));
             }
+            if let Some(session) = summary.session_ignored.filter(|&n| n > 0) {
+                parts.push(format!("{} session-ignored", session));
+            }
 
-            println!(
+            outln!(
+                self.out,
                 "  {}",
                 format!(
                     "{} finding(s) suppressed ({})",
@@ -193,6 +533,90 @@ impl OutputFormatter for TerminalFormatter {
             );
         }
 
+        // Local `revet ignore` entries, so hidden findings are never silent
+        if let Some(session) = summary.session_ignored {
+            outln!(
+                self.out,
+                "  {}",
+                format!(
+                    "Session ignores active: {} finding(s) hidden (see `revet ignore list`)",
+                    session
+                )
+                .dimmed()
+            );
+        }
+
+        // Complexity findings the delta rule deferred
+        if let Some(delta) = &summary.complexity_delta {
+            outln!(
+                self.out,
+                "  {}",
+                format!(
+                    "Complexity delta: {} reported, {} deferred (absolute mode: {})",
+                    delta.reported, delta.deferred, delta.absolute
+                )
+                .dimmed()
+            );
+        }
+
+        // Documentation coverage of the public surface
+        if let Some(docs) = &summary.doc_coverage {
+            outln!(
+                self.out,
+                "  {}",
+                format!(
+                    "Documentation coverage: {:.1}% ({} of {} public symbols)",
+                    docs.total.percent, docs.total.documented, docs.total.public
+                )
+                .dimmed()
+            );
+        }
+
+        // Phases --time-budget left out
+        if let Some(budget) = &summary.budget {
+            let outcome = if budget.budget_exceeded {
+                "exceeded, partial coverage"
+            } else {
+                "met"
+            };
+            outln!(
+                self.out,
+                "  {}",
+                format!("Time budget {:.0}s: {}", budget.time_budget_secs, outcome).dimmed()
+            );
+            for skipped in &budget.skipped_phases {
+                outln!(self.out, "    {}", format!("skipped: {}", skipped).dimmed());
+            }
+        }
+
+        // Features a partial clone left short of objects
+        if !summary.degraded.is_empty() {
+            outln!(
+                self.out,
+                "  {}",
+                "Partial clone: some features ran degraded".yellow()
+            );
+            for degraded in &summary.degraded {
+                outln!(self.out, "    {}", degraded.to_string().dimmed());
+            }
+        }
+
+        // Load shed under `[performance] max_memory_mb`
+        if let Some(memory) = summary.memory.as_ref().filter(|m| !m.shed.is_empty()) {
+            let outcome = match &memory.stopped_before {
+                Some(stage) => format!("exceeded, stopped before {}, partial findings", stage),
+                None => format!("load shed, peak {} MB", memory.peak_mb),
+            };
+            outln!(
+                self.out,
+                "  {}",
+                format!("Memory ceiling {} MB: {}", memory.max_memory_mb, outcome).dimmed()
+            );
+            for step in &memory.shed {
+                outln!(self.out, "    {}", format!("shed: {}", step).dimmed());
+            }
+        }
+
         // Files analyzed with optional language breakdown
         let lang_detail = if summary.files_by_language.is_empty() {
             String::new()
@@ -205,7 +629,8 @@ impl OutputFormatter for TerminalFormatter {
                 .collect();
             format!(" ({})", parts.join(", "))
         };
-        println!(
+        outln!(
+            self.out,
             "  {}",
             format!(
                 "{} files analyzed{} \u{00b7} {} nodes parsed",
@@ -214,21 +639,55 @@ impl OutputFormatter for TerminalFormatter {
             .dimmed()
         );
 
-        println!(
+        if let Some(repro) = &self.reproducibility {
+            let overrides = if repro.overrides.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", repro.overrides.join(" "))
+            };
+            outln!(
+                self.out,
+                "  {}",
+                format!("Profile: {}{}", repro.profile, overrides).dimmed()
+            );
+            if !repro.rollout.is_empty() {
+                let decisions: Vec<String> = repro
+                    .rollout
+                    .iter()
+                    .map(|d| {
+                        let mode = if d.active { "active" } else { "shadow" };
+                        format!("{} {}% {}", d.module, d.percent, mode)
+                    })
+                    .collect();
+                outln!(
+                    self.out,
+                    "  {}",
+                    format!("Rollout: {}", decisions.join(", ")).dimmed()
+                );
+            }
+        }
+
+        outln!(
+            self.out,
             "  {}",
-            format!("Time: {:.1}s", elapsed.as_secs_f64()).green()
+            format!("Time: {}", self.out.elapsed(elapsed)).green()
         );
 
         if let Some(id) = run_id {
-            println!("  {}", format!("Run log: revet log --show {}", id).dimmed());
+            outln!(
+                self.out,
+                "  {}",
+                format!("Run log: revet log --show {}", self.out.run_id(id)).dimmed()
+            );
         }
     }
 
     fn write_no_files(&mut self, elapsed: Duration) {
-        println!("  {}", "No supported files found.".dimmed());
-        println!(
+        outln!(self.out, "  {}", "No supported files found.".dimmed());
+        outln!(
+            self.out,
             "  {}",
-            format!("Time: {:.1}s", elapsed.as_secs_f64()).green()
+            format!("Time: {}", self.out.elapsed(elapsed)).green()
         );
     }
 }
@@ -251,8 +710,8 @@ fn format_debt(minutes: usize) -> String {
 
 // ── Rendering helpers ────────────────────────────────────────────────────────
 
-fn finding_block(f: &Finding, repo_path: &Path) -> String {
-    let label = f.id.split('-').next().unwrap_or(&f.id);
+fn finding_block(f: &Finding, repo_path: &Path, locale: Locale, details: bool) -> String {
+    let label = prefix_of(&f.id);
 
     let (icon, colored_label) = match f.severity {
         Severity::Error => ("✗".red().bold().to_string(), label.red().bold().to_string()),
@@ -263,19 +722,19 @@ fn finding_block(f: &Finding, repo_path: &Path) -> String {
         Severity::Info => ("·".blue().to_string(), label.blue().to_string()),
     };
 
-    let display = f.file.strip_prefix(repo_path).unwrap_or(&f.file);
-    let file_line = if f.line > 0 {
-        format!("{}:{}", display.display(), f.line)
-            .cyan()
-            .to_string()
-    } else {
-        display.display().to_string().cyan().to_string()
-    };
+    let file_line = location(f, repo_path).cyan().to_string();
 
     let pipe = "|".dimmed();
-    let mut lines = vec![format!("  {}  {}   {}", icon, colored_label, file_line)];
+    let mut header = format!("  {}  {}   {}", icon, colored_label, file_line);
+    if !f.confidence.is_high() {
+        header.push_str(&format!(
+            "  {}",
+            format!("({} confidence)", f.confidence).dimmed()
+        ));
+    }
+    let mut lines = vec![header];
 
-    for msg_line in f.message.lines() {
+    for msg_line in f.localized_message(locale).lines() {
         // Lines starting with "→" are caller/path references — highlight in cyan
         let trimmed = msg_line.trim_start();
         if trimmed.starts_with('\u{2192}') {
@@ -285,6 +744,12 @@ fn finding_block(f: &Finding, repo_path: &Path) -> String {
         }
     }
 
+    if details {
+        for line in f.details.iter().flat_map(|d| d.lines()) {
+            lines.push(format!("  {}    {}", pipe, line.dimmed()));
+        }
+    }
+
     // Render caller locations as cyan arrow lines
     for caller in &f.callers {
         lines.push(format!(
@@ -294,9 +759,14 @@ fn finding_block(f: &Finding, repo_path: &Path) -> String {
         ));
     }
 
-    if let Some(s) = &f.suggestion {
+    if let Some(s) = f.localized_suggestion(locale) {
         lines.push(format!("  {}  {}", pipe, format!("Fix: {}", s).dimmed()));
     }
+    if let Some(code) = &f.suggestion_code {
+        for code_line in code.snippet.lines() {
+            lines.push(format!("  {}      {}", pipe, code_line.cyan()));
+        }
+    }
 
     if let Some(note) = &f.ai_note {
         let prefix = if f.ai_false_positive {
@@ -314,9 +784,22 @@ fn finding_block(f: &Finding, repo_path: &Path) -> String {
     lines.join("\n")
 }
 
-fn suppressed_block(sf: &SuppressedFinding, repo_path: &Path) -> String {
+/// `path:line:column`, dropping the parts that are unknown
+fn location(f: &Finding, repo_path: &Path) -> String {
+    if f.is_repository_level() {
+        return "(repository)".to_string();
+    }
+    let display = f.file.strip_prefix(repo_path).unwrap_or(&f.file);
+    match (f.line, f.column) {
+        (0, _) => display.display().to_string(),
+        (line, Some(column)) => format!("{}:{}:{}", display.display(), line, column),
+        (line, None) => format!("{}:{}", display.display(), line),
+    }
+}
+
+fn suppressed_block(sf: &SuppressedFinding, repo_path: &Path, locale: Locale) -> String {
     let f = &sf.finding;
-    let label = f.id.split('-').next().unwrap_or(&f.id);
+    let label = prefix_of(&f.id);
 
     let icon = match f.severity {
         Severity::Error => "✗",
@@ -324,12 +807,7 @@ fn suppressed_block(sf: &SuppressedFinding, repo_path: &Path) -> String {
         Severity::Info => "·",
     };
 
-    let display = f.file.strip_prefix(repo_path).unwrap_or(&f.file);
-    let file_line = if f.line > 0 {
-        format!("{}:{}", display.display(), f.line)
-    } else {
-        display.display().to_string()
-    };
+    let file_line = location(f, repo_path);
 
     let header = format!("  {}  {}   {}", icon, label, file_line).dimmed();
     let pipe = "|".dimmed();
@@ -338,7 +816,7 @@ fn suppressed_block(sf: &SuppressedFinding, repo_path: &Path) -> String {
         "{}\n  {}  {}\n  {}  {}",
         header,
         pipe,
-        f.message.as_str().dimmed(),
+        f.localized_message(locale).dimmed(),
         pipe,
         format!("[suppressed: {}]", sf.reason).dimmed()
     )
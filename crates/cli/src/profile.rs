@@ -0,0 +1,142 @@
+//! `--profile` resolution — the chosen profile plus explicit flags, resolved
+//! once into the [`ProfileSettings`] that review, diff and watch all read
+
+use anyhow::{bail, Result};
+use revet_core::analyzer::registry::Registry;
+use revet_core::analyzer::rules;
+use revet_core::config::{ModulesConfig, RuleSelection};
+use revet_core::profile::FIRST_RUN_PROFILE;
+use revet_core::{CodeKind, ProfileSettings, RevetConfig, Scope};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::Cli;
+
+/// Resolve the profile for this run, layer explicit flags on top (flags win
+/// and are recorded as overrides), and write the result into `config`
+pub fn resolve(cli: &Cli, config: &mut RevetConfig) -> Result<ProfileSettings> {
+    resolve_named(cli, cli.profile.as_deref(), config)
+}
+
+/// [`resolve`] for a review of `repo_path`: the `first-run` profile when no
+/// `.revet.toml` exists in it or above it and neither `--profile`, `--full`
+/// nor `--module` picks the analysis
+pub fn resolve_review(
+    cli: &Cli,
+    repo_path: &Path,
+    config: &mut RevetConfig,
+) -> Result<ProfileSettings> {
+    let first_run = cli.profile.is_none()
+        && !cli.full
+        && cli.module.is_none()
+        && RevetConfig::find_path(repo_path).is_none();
+    let name = match first_run {
+        true => Some(FIRST_RUN_PROFILE),
+        false => cli.profile.as_deref(),
+    };
+    resolve_named(cli, name, config)
+}
+
+fn resolve_named(
+    cli: &Cli,
+    name: Option<&str>,
+    config: &mut RevetConfig,
+) -> Result<ProfileSettings> {
+    let mut settings = ProfileSettings::resolve(name, config)?;
+
+    if cli.full {
+        settings.scope = Scope::Full;
+        settings.record_override("--full");
+    }
+    // A project is reviewed whole, so its result depends on its inputs alone
+    if let Some(project) = &cli.project {
+        settings.scope = Scope::Full;
+        settings.record_override(format!("--project {}", project.display()));
+    }
+    if let Some(base) = &cli.diff {
+        settings.diff_base = base.clone();
+        settings.record_override(format!("--diff {}", base));
+    }
+    // Never widens to a full scan when nothing changed
+    if let Some(rev) = &cli.changed_since {
+        settings.scope = Scope::Changed;
+        settings.diff_base = rev.clone();
+        settings.record_override(format!("--changed-since {}", rev));
+    }
+    if cli.ai {
+        settings.ai = true;
+        settings.record_override("--ai");
+    }
+    if let Some(only) = cli.only {
+        let kind = CodeKind::from(only);
+        settings.only = Some(kind);
+        settings.record_override(format!("--only {}", kind));
+    }
+    if let Some(fail_on) = &cli.fail_on {
+        settings.fail_on = fail_on.clone();
+        settings.record_override(format!("--fail-on {}", fail_on));
+    }
+    if cli.no_rollout_sampling {
+        settings.rollout_sampling = false;
+        settings.record_override("--no-rollout-sampling");
+    }
+    // `--module name` enables a whole module, `--module name:rule` only
+    // the named sub-rules of it
+    let mut only: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    if let Some(modules) = &cli.module {
+        let mut wanted: Vec<String> = Vec::new();
+        let mut whole: Vec<String> = Vec::new();
+        for entry in modules {
+            let entry = entry.replace('-', "_");
+            let (module, rule) = match entry.split_once(':') {
+                Some((module, rule)) => (module.to_string(), Some(rule.to_string())),
+                None => (entry, None),
+            };
+            if !ModulesConfig::NAMES.contains(&module.as_str()) {
+                bail!(
+                    "--module: unknown module {:?} (known: {})",
+                    module,
+                    ModulesConfig::NAMES.join(", ")
+                );
+            }
+            match rule {
+                Some(rule) => {
+                    if let Err(e) = rules::check(&module, [rule.as_str()]) {
+                        bail!("--module: {}", e);
+                    }
+                    only.entry(module.clone()).or_default().push(rule);
+                }
+                None => whole.push(module.clone()),
+            }
+            wanted.push(module);
+        }
+        only.retain(|module, _| !whole.contains(module));
+        for name in ModulesConfig::NAMES {
+            settings
+                .modules
+                .insert(name.to_string(), wanted.iter().any(|m| m == name));
+        }
+        settings.record_override(format!("--module {}", modules.join(",")));
+    }
+
+    settings.apply(config);
+    for (module, rules) in only {
+        config.analyzers.rules.insert(
+            module,
+            RuleSelection {
+                only: rules,
+                ..Default::default()
+            },
+        );
+    }
+    if let Some(error) = rules::check_selections(config).into_iter().next() {
+        bail!("{}", error);
+    }
+    if let Err(conflicts) = Registry::from_config(config) {
+        bail!("{}", conflicts.join("\n"));
+    }
+    if cli.no_redact {
+        config.output.redact_secrets = false;
+    }
+    Ok(settings)
+}
@@ -2,6 +2,19 @@
 
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Hides all [`Step`] output (set while `revet multi` reviews repos in parallel)
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Hide or show [`Step`] spinners and their finish lines process-wide.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
 
 pub fn create_spinner(msg: &str) -> ProgressBar {
     let pb = ProgressBar::new_spinner();
@@ -41,6 +54,12 @@ impl Step {
     /// Start a new spinner step with the given label.
     pub fn new(label: impl Into<String>) -> Self {
         let label = label.into();
+        if is_quiet() {
+            return Self {
+                pb: ProgressBar::hidden(),
+                label,
+            };
+        }
         let pb = ProgressBar::new_spinner();
         pb.set_style(
             ProgressStyle::default_spinner()
@@ -60,12 +79,18 @@ impl Step {
     /// Finish successfully: prints `"  label... done — {summary}"`.
     pub fn finish(&self, summary: &str) {
         self.pb.finish_and_clear();
+        if is_quiet() {
+            return;
+        }
         eprintln!("  {}... {} — {}", self.label, "done".green(), summary);
     }
 
     /// Finish as skipped / not-applicable: prints `"  {msg}"` dimmed.
     pub fn skip(&self, msg: &str) {
         self.pb.finish_and_clear();
+        if is_quiet() {
+            return;
+        }
         eprintln!("  {}", msg.dimmed());
     }
 
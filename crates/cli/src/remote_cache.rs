@@ -0,0 +1,215 @@
+//! Remote cache backends for `[cache.remote]`
+//!
+//! - `https://host/prefix` (or `http://`): `GET <url>/<key>` answers 200 with
+//!   the blob or 404 on a miss, `PUT <url>/<key>` stores it. When the
+//!   variable named by `token_env` is set, requests carry it as a bearer
+//!   token.
+//! - `s3://bucket/prefix`, in builds with the `s3-cache` feature: objects
+//!   `<prefix>/<key>`, with requests signed using credentials from the
+//!   standard AWS chain (environment, profile, SSO, instance or task role).
+//!   `AWS_ENDPOINT_URL_S3` or `AWS_ENDPOINT_URL` selects an S3-compatible
+//!   server.
+//!
+//! A backend that cannot be set up behaves like one whose first request
+//! failed: one notice, then the local cache only.
+
+use anyhow::{bail, Context, Result};
+use reqwest::blocking::{Client, Response};
+use reqwest::StatusCode;
+use revet_core::config::RemoteCacheConfig;
+use revet_core::{RemoteCache, RemoteStore};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The remote cache for the checkout at `repo_root`, or `None` without
+/// `[cache.remote] url`
+pub fn open(config: &RemoteCacheConfig, repo_root: &Path) -> Option<Arc<RemoteCache>> {
+    let url = config.url.as_deref()?;
+    let cache = match store(url, config) {
+        Ok(store) => RemoteCache::new(store, url, repo_root),
+        Err(e) => RemoteCache::unavailable(url, &e),
+    };
+    Some(Arc::new(cache))
+}
+
+/// The backend for `url`
+pub fn store(url: &str, config: &RemoteCacheConfig) -> Result<Box<dyn RemoteStore>> {
+    let client = Client::builder()
+        .user_agent(concat!("revet/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_millis(config.timeout_ms))
+        .build()
+        .context("Failed to build HTTP client")?;
+    if let Some(location) = url.strip_prefix("s3://") {
+        return s3::store(client, location);
+    }
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        bail!("unsupported URL scheme");
+    }
+    Ok(Box::new(HttpStore {
+        client,
+        base: url.trim_end_matches('/').to_string(),
+        token: std::env::var(&config.token_env)
+            .ok()
+            .filter(|t| !t.is_empty()),
+    }))
+}
+
+/// Blob of a GET response: `None` on 404, an error on other failures
+fn fetched(response: Response) -> Result<Option<Vec<u8>>> {
+    match response.status() {
+        StatusCode::NOT_FOUND => Ok(None),
+        status if status.is_success() => Ok(Some(response.bytes()?.to_vec())),
+        status => bail!("GET answered {}", status),
+    }
+}
+
+fn stored(response: Response) -> Result<()> {
+    let status = response.status();
+    if !status.is_success() {
+        bail!("PUT answered {}", status);
+    }
+    Ok(())
+}
+
+/// A plain HTTP server
+struct HttpStore {
+    client: Client,
+    base: String,
+    token: Option<String>,
+}
+
+impl HttpStore {
+    fn request(&self, method: reqwest::Method, key: &str) -> reqwest::blocking::RequestBuilder {
+        let request = self
+            .client
+            .request(method, format!("{}/{}", self.base, key));
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+}
+
+impl RemoteStore for HttpStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        fetched(self.request(reqwest::Method::GET, key).send()?)
+    }
+
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<()> {
+        stored(
+            self.request(reqwest::Method::PUT, key)
+                .body(bytes.to_vec())
+                .send()?,
+        )
+    }
+}
+
+#[cfg(not(feature = "s3-cache"))]
+mod s3 {
+    use super::*;
+
+    pub fn store(_client: Client, _location: &str) -> Result<Box<dyn RemoteStore>> {
+        bail!("s3:// URLs need a build with the `s3-cache` feature")
+    }
+}
+
+#[cfg(feature = "s3-cache")]
+mod s3 {
+    use super::*;
+    use aws_config::BehaviorVersion;
+    use aws_credential_types::provider::ProvideCredentials;
+    use aws_credential_types::Credentials;
+    use aws_sigv4::http_request::{
+        sign, PayloadChecksumKind, PercentEncodingMode, SignableBody, SignableRequest,
+        SigningSettings, UriPathNormalizationMode,
+    };
+    use aws_sigv4::sign::v4;
+    use std::time::SystemTime;
+
+    /// An S3 bucket, addressed as `bucket/prefix`
+    pub fn store(client: Client, location: &str) -> Result<Box<dyn RemoteStore>> {
+        let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let config = runtime.block_on(aws_config::load_defaults(BehaviorVersion::latest()));
+        let region = config
+            .region()
+            .map_or_else(|| "us-east-1".to_string(), |r| r.to_string());
+        let provider = config
+            .credentials_provider()
+            .context("no AWS credentials provider")?;
+        let credentials = runtime
+            .block_on(provider.provide_credentials())
+            .context("no AWS credentials")?;
+
+        let endpoint = ["AWS_ENDPOINT_URL_S3", "AWS_ENDPOINT_URL"]
+            .iter()
+            .find_map(|name| std::env::var(name).ok().filter(|v| !v.is_empty()));
+        let mut base = match endpoint {
+            // Path-style, which S3-compatible servers expect
+            Some(endpoint) => format!("{}/{}", endpoint.trim_end_matches('/'), bucket),
+            None => format!("https://{}.s3.{}.amazonaws.com", bucket, region),
+        };
+        let prefix = prefix.trim_matches('/');
+        if !prefix.is_empty() {
+            base = format!("{}/{}", base, prefix);
+        }
+        Ok(Box::new(S3Store {
+            client,
+            base,
+            region,
+            credentials,
+        }))
+    }
+
+    struct S3Store {
+        client: Client,
+        base: String,
+        region: String,
+        credentials: Credentials,
+    }
+
+    impl S3Store {
+        fn send(&self, method: reqwest::Method, key: &str, body: &[u8]) -> Result<Response> {
+            let url = format!("{}/{}", self.base, key);
+            let identity = self.credentials.clone().into();
+            let mut settings = SigningSettings::default();
+            settings.payload_checksum_kind = PayloadChecksumKind::XAmzSha256;
+            settings.percent_encoding_mode = PercentEncodingMode::Single;
+            settings.uri_path_normalization_mode = UriPathNormalizationMode::Disabled;
+            let params = v4::SigningParams::builder()
+                .identity(&identity)
+                .region(&self.region)
+                .name("s3")
+                .time(SystemTime::now())
+                .settings(settings)
+                .build()?
+                .into();
+            let signable = SignableRequest::new(
+                method.as_str(),
+                url.as_str(),
+                std::iter::empty(),
+                SignableBody::Bytes(body),
+            )?;
+            let (instructions, _) = sign(signable, &params)?.into_parts();
+
+            let mut request = self.client.request(method, &url).body(body.to_vec());
+            for (name, value) in instructions.headers() {
+                request = request.header(name, value);
+            }
+            Ok(request.send()?)
+        }
+    }
+
+    impl RemoteStore for S3Store {
+        fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+            fetched(self.send(reqwest::Method::GET, key, &[])?)
+        }
+
+        fn put(&self, key: &str, bytes: &[u8]) -> Result<()> {
+            stored(self.send(reqwest::Method::PUT, key, bytes)?)
+        }
+    }
+}
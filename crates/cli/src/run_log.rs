@@ -1,12 +1,43 @@
-//! Run log — persists every review run to `.revet-cache/runs/<id>.json`.
+//! Run log — persists every review run to `runs/<id>.json` in the state
+//! directory (`.revet-cache/` unless relocated, see [`StoragePaths`]).
+//!
+//! The newest review's findings also go to `last-run.json`, with the
+//! source lines around each, so `revet explain <id>` works offline after
+//! the code has changed.
+//!
+//! Full runs with a baseline also record its entries and what changed since
+//! the previous full run on the branch (see [`BaselineRecord`]), for
+//! `revet trend`.
 
 use anyhow::{Context, Result};
-use revet_core::{Finding, ReviewSummary, SuppressedFinding};
+use revet_core::analyzer::complexity::delta::DeltaSummary;
+use revet_core::analyzer::docs::DocCoverage;
+use revet_core::analyzer::hotspots::Hotspot;
+use revet_core::analyzer::ownership::codeowners::CodeOwners;
+use revet_core::analyzer::ownership::Orphan;
+use revet_core::baseline::delta::{Delta, Snapshot};
+use revet_core::storage::{LAST_RUN_FILE, RUNS_DIR};
+use revet_core::{BudgetReport, Degradation, MemoryReport, ShadowModule, StoragePaths};
+use revet_core::{
+    CodeKind, CoverageReport, Finding, FindingDetails, FixKind, ReviewSummary, SecretIndex,
+    SuppressedFinding,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-const RUNS_DIR: &str = ".revet-cache/runs";
+/// Top of the hotspot ranking kept in each run log
+const LOGGED_HOTSPOTS: usize = 20;
+
+/// Largest orphans kept in each run log
+const LOGGED_ORPHANS: usize = 20;
+
+/// Least documented files kept in each run log
+const LOGGED_DOC_FILES: usize = 20;
+
+/// Source lines kept either side of each finding in `last-run.json`
+const SNIPPET_CONTEXT: usize = 3;
 
 // ── On-disk structures ───────────────────────────────────────────
 
@@ -18,8 +49,62 @@ pub struct RunLog {
     pub duration_secs: f64,
     pub files_analyzed: usize,
     pub nodes_parsed: usize,
+    /// Branch checked out, unless HEAD was detached
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Whether the whole repository was reviewed, rather than a diff or a
+    /// `--project`
+    #[serde(default)]
+    pub full: bool,
     pub summary: RunSummary,
     pub findings: Vec<RunFinding>,
+    /// Highest-scoring files of the hotspot ranking, when `HOT` ran
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hotspots: Vec<Hotspot>,
+    /// Largest orphaned files or directories, when `OWN` ran
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub orphans: Vec<Orphan>,
+    /// Coverage report, when the run had `--coverage-report`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<CoverageReport>,
+    /// Baseline entries and their changes, in full runs with a baseline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<BaselineRecord>,
+}
+
+/// A full run's baseline entries, and the changes since the previous full
+/// run on the same branch that recorded them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineRecord {
+    /// ID of the run `delta` counts from; `None` for the first run recorded
+    /// on the branch, whose `delta` is empty
+    pub since: Option<String>,
+    pub delta: Delta,
+    pub snapshot: Snapshot,
+}
+
+impl BaselineRecord {
+    /// The record of `snapshot`, its changes counted from the newest record
+    /// on `branch` and attributed with `owners`
+    pub fn since_last(
+        paths: &StoragePaths,
+        branch: Option<&str>,
+        snapshot: Snapshot,
+        owners: &CodeOwners,
+    ) -> Self {
+        match branch.and_then(|branch| last_baseline(paths, branch)) {
+            Some((since, previous)) => Self {
+                since: Some(since),
+                delta: Delta::between(&previous.snapshot, &snapshot, owners),
+                snapshot,
+            },
+            None => Self {
+                since: None,
+                delta: Delta::default(),
+                snapshot,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -28,6 +113,29 @@ pub struct RunSummary {
     pub warnings: usize,
     pub info: usize,
     pub suppressed: usize,
+    #[serde(default)]
+    pub generated: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complexity_delta: Option<DeltaSummary>,
+    /// Documentation coverage, when `DOC` ran; files only the least
+    /// covered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_coverage: Option<DocCoverage>,
+    /// Phases skipped to stay within `--time-budget`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<BudgetReport>,
+    /// Load shed to stay under `[performance] max_memory_mb`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemoryReport>,
+    /// Findings of modules in shadow mode (`rollout_percent`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shadow: Vec<ShadowModule>,
+    /// Findings hidden by `revet ignore` entries, when any applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_ignored: Option<usize>,
+    /// Features that skipped objects a partial clone lacks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub degraded: Vec<Degradation>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,6 +147,75 @@ pub struct RunFinding {
     pub line: usize,
     pub suppressed: bool,
     pub suppression_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub generated: bool,
+    #[serde(default, skip_serializing_if = "CodeKind::is_production")]
+    pub code_kind: CodeKind,
+    /// External tool of an imported finding (`revet import`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_tool: Option<String>,
+    /// Long-form content behind `message`, for `revet explain <id>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<FindingDetails>,
+}
+
+/// Every finding of the newest review, as `revet explain` shows them
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LastRun {
+    /// ID of the run log written with it
+    pub run_id: String,
+    pub findings: Vec<LastRunFinding>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LastRunFinding {
+    pub id: String,
+    pub severity: String,
+    pub message: String,
+    /// Relative to the repository root; empty for repository-level findings
+    pub file: String,
+    pub line: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    /// Description of an imported finding's external rule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix_kind: Option<FixKind>,
+    /// Lines around `line` as they were in the run, secrets masked
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snippet: Vec<SnippetLine>,
+    /// Why the run suppressed it, when it did
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppression_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<FindingDetails>,
+}
+
+impl LastRunFinding {
+    /// Whether `revet --fix` changes the code for it, rather than only
+    /// suggesting a change
+    pub fn is_fixable(&self) -> bool {
+        !matches!(self.fix_kind, None | Some(FixKind::Suggestion))
+    }
+}
+
+/// One source line of a [`LastRunFinding`] snippet
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnippetLine {
+    pub line: usize,
+    pub text: String,
+}
+
+/// What a run covered, recorded with its log
+#[derive(Debug, Clone, Default)]
+pub struct RunScope {
+    pub branch: Option<String>,
+    pub full: bool,
 }
 
 /// A brief entry shown in `revet log` listings.
@@ -55,19 +232,25 @@ pub struct RunEntry {
 
 // ── Write ────────────────────────────────────────────────────────
 
-/// Persist a completed review run to `.revet-cache/runs/<id>.json`.
+/// Persist a completed review run to `runs/<id>.json` in the data directory.
 ///
 /// The `id` is the millisecond Unix timestamp at the start of the run.
+#[allow(clippy::too_many_arguments)]
 pub fn save_run_log(
-    repo_path: &Path,
+    paths: &StoragePaths,
     id: &str,
     duration_secs: f64,
     findings: &[Finding],
     suppressed: &[SuppressedFinding],
     summary: &ReviewSummary,
+    hotspots: &[Hotspot],
+    orphans: &[Orphan],
+    coverage: Option<CoverageReport>,
+    scope: &RunScope,
+    baseline: Option<BaselineRecord>,
     repo_root: &Path,
 ) -> Result<()> {
-    let runs_dir = repo_path.join(RUNS_DIR);
+    let runs_dir = paths.write_path(RUNS_DIR);
     std::fs::create_dir_all(&runs_dir)
         .with_context(|| format!("create runs dir {}", runs_dir.display()))?;
 
@@ -81,7 +264,7 @@ pub fn save_run_log(
         .map(|f| RunFinding {
             id: f.id.clone(),
             severity: f.severity.to_string(),
-            message: f.message.clone(),
+            message: f.redacted_message().to_string(),
             file: f
                 .file
                 .strip_prefix(repo_root)
@@ -91,6 +274,11 @@ pub fn save_run_log(
             line: f.line,
             suppressed: false,
             suppression_reason: None,
+            rule: f.rule.clone(),
+            generated: f.generated,
+            code_kind: f.code_kind,
+            source_tool: f.source_tool.clone(),
+            details: f.details.clone(),
         })
         .collect();
 
@@ -98,7 +286,7 @@ pub fn save_run_log(
         run_findings.push(RunFinding {
             id: sf.finding.id.clone(),
             severity: sf.finding.severity.to_string(),
-            message: sf.finding.message.clone(),
+            message: sf.finding.redacted_message().to_string(),
             file: sf
                 .finding
                 .file
@@ -109,6 +297,11 @@ pub fn save_run_log(
             line: sf.finding.line,
             suppressed: true,
             suppression_reason: Some(sf.reason.clone()),
+            rule: sf.finding.rule.clone(),
+            generated: sf.finding.generated,
+            code_kind: sf.finding.code_kind,
+            source_tool: sf.finding.source_tool.clone(),
+            details: sf.finding.details.clone(),
         });
     }
 
@@ -119,13 +312,30 @@ pub fn save_run_log(
         duration_secs,
         files_analyzed: summary.files_analyzed,
         nodes_parsed: summary.nodes_parsed,
+        branch: scope.branch.clone(),
+        full: scope.full,
         summary: RunSummary {
             errors: summary.errors,
             warnings: summary.warnings,
             info: summary.info,
             suppressed: suppressed.len(),
+            generated: summary.generated,
+            complexity_delta: summary.complexity_delta,
+            doc_coverage: summary.doc_coverage.clone().map(|mut coverage| {
+                coverage.files.truncate(LOGGED_DOC_FILES);
+                coverage
+            }),
+            budget: summary.budget.clone(),
+            memory: summary.memory.clone(),
+            shadow: summary.shadow.clone(),
+            session_ignored: summary.session_ignored,
+            degraded: summary.degraded.clone(),
         },
         findings: run_findings,
+        hotspots: hotspots.iter().take(LOGGED_HOTSPOTS).cloned().collect(),
+        orphans: orphans.iter().take(LOGGED_ORPHANS).cloned().collect(),
+        coverage,
+        baseline,
     };
 
     let path = runs_dir.join(format!("{}.json", id));
@@ -135,22 +345,126 @@ pub fn save_run_log(
     Ok(())
 }
 
+/// Replace `last-run.json` with the findings of run `id`, reported and
+/// suppressed, and the source lines around each.
+pub fn save_last_run(
+    paths: &StoragePaths,
+    id: &str,
+    findings: &[Finding],
+    suppressed: &[SuppressedFinding],
+    repo_root: &Path,
+) -> Result<()> {
+    paths.ensure_data_dir()?;
+    let all: Vec<(&Finding, Option<&str>)> = findings
+        .iter()
+        .map(|f| (f, None))
+        .chain(
+            suppressed
+                .iter()
+                .map(|s| (&s.finding, Some(s.reason.as_str()))),
+        )
+        .collect();
+    // Secrets any finding recorded, masked in every snippet that shows them
+    let with_secrets: Vec<Finding> = all
+        .iter()
+        .filter(|(f, _)| !f.secret_spans.is_empty())
+        .map(|(f, _)| (*f).clone())
+        .collect();
+    let secrets = SecretIndex::from_findings(&with_secrets);
+    let mut sources: HashMap<&Path, Option<Vec<String>>> = HashMap::new();
+
+    let findings = all
+        .iter()
+        .map(|(f, reason)| {
+            let lines = sources.entry(&f.file).or_insert_with(|| {
+                std::fs::read_to_string(&f.file)
+                    .ok()
+                    .map(|content| content.lines().map(str::to_string).collect())
+            });
+            LastRunFinding {
+                id: f.id.clone(),
+                severity: f.severity.to_string(),
+                message: f.redacted_message().to_string(),
+                file: f
+                    .file
+                    .strip_prefix(repo_root)
+                    .unwrap_or(&f.file)
+                    .display()
+                    .to_string(),
+                line: f.line,
+                rule: f.rule.clone(),
+                rule_description: f.rule_description.clone(),
+                suggestion: f.suggestion.clone(),
+                fix_kind: f.fix_kind.clone(),
+                snippet: lines
+                    .as_deref()
+                    .map(|lines| snippet(lines, f, &secrets))
+                    .unwrap_or_default(),
+                suppression_reason: reason.map(str::to_string),
+                details: f.details.clone(),
+            }
+        })
+        .collect();
+
+    let path = paths.write_path(LAST_RUN_FILE);
+    let json = serde_json::to_string_pretty(&LastRun {
+        run_id: id.to_string(),
+        findings,
+    })?;
+    std::fs::write(&path, json).with_context(|| format!("write {}", path.display()))?;
+    Ok(())
+}
+
+/// [`SNIPPET_CONTEXT`] lines either side of `finding`'s line
+fn snippet(lines: &[String], finding: &Finding, secrets: &SecretIndex) -> Vec<SnippetLine> {
+    if finding.line == 0 || finding.line > lines.len() {
+        return Vec::new();
+    }
+    let first = finding.line.saturating_sub(SNIPPET_CONTEXT).max(1);
+    let last = (finding.line + SNIPPET_CONTEXT).min(lines.len());
+    (first..=last)
+        .map(|line| SnippetLine {
+            line,
+            text: secrets.redact_line(&finding.file, line, &lines[line - 1]),
+        })
+        .collect()
+}
+
 // ── Read ─────────────────────────────────────────────────────────
 
-/// List all run log entries, sorted newest-first.
-pub fn list_runs(repo_path: &Path) -> Result<Vec<RunEntry>> {
-    let runs_dir = repo_path.join(RUNS_DIR);
-    if !runs_dir.exists() {
-        return Ok(vec![]);
+/// Run log directories of `paths`: the data directory's, then the legacy
+/// in-repo one while it still exists
+fn runs_dirs(paths: &StoragePaths) -> Vec<PathBuf> {
+    let mut dirs = vec![paths.write_path(RUNS_DIR)];
+    if paths.is_relocated() {
+        dirs.push(paths.legacy_dir().join(RUNS_DIR));
     }
+    dirs
+}
+
+/// Every `<id>.json` in the run log directories; the data directory wins
+/// when both hold an ID
+fn run_files(paths: &StoragePaths) -> Vec<PathBuf> {
+    let mut seen = std::collections::HashSet::new();
+    runs_dirs(paths)
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                return None;
+            }
+            let name = path.file_name()?.to_os_string();
+            seen.insert(name).then_some(path)
+        })
+        .collect()
+}
 
+/// List all run log entries, sorted newest-first.
+pub fn list_runs(paths: &StoragePaths) -> Result<Vec<RunEntry>> {
     let mut entries = Vec::new();
-    for entry in std::fs::read_dir(&runs_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.extension().and_then(|e| e.to_str()) != Some("json") {
-            continue;
-        }
+    for path in run_files(paths) {
         if let Ok(log) = load_run_log_from_path(&path) {
             let findings_kept = log.findings.iter().filter(|f| !f.suppressed).count();
             entries.push(RunEntry {
@@ -166,13 +480,51 @@ pub fn list_runs(repo_path: &Path) -> Result<Vec<RunEntry>> {
     }
 
     // Newest first
-    entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    entries.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
     Ok(entries)
 }
 
-/// Load a run log by its ID from the given repo.
-pub fn load_run_log(repo_path: &Path, id: &str) -> Result<RunLog> {
-    let path = repo_path.join(RUNS_DIR).join(format!("{}.json", id));
+/// Full runs on `branch`, newest first. Diff and `--project` runs are
+/// left out: a finding missing from them may just be out of their scope.
+pub fn branch_history(paths: &StoragePaths, branch: &str) -> Vec<RunLog> {
+    let mut logs: Vec<RunLog> = run_files(paths)
+        .iter()
+        .filter_map(|path| load_run_log_from_path(path).ok())
+        .filter(|log| log.full && log.branch.as_deref() == Some(branch))
+        .collect();
+    logs.sort_by_key(|log| std::cmp::Reverse(log.id.parse::<u64>().unwrap_or(0)));
+    logs
+}
+
+/// The newest full run on `branch` that recorded the baseline, with its ID
+pub fn last_baseline(paths: &StoragePaths, branch: &str) -> Option<(String, BaselineRecord)> {
+    branch_history(paths, branch)
+        .into_iter()
+        .find_map(|log| Some((log.id, log.baseline?)))
+}
+
+/// ID of the newest run log, from file names alone (IDs are millisecond
+/// timestamps).
+pub fn latest_run_id(paths: &StoragePaths) -> Option<String> {
+    run_files(paths)
+        .iter()
+        .filter_map(|path| {
+            let id = path.file_stem()?.to_str()?;
+            id.parse::<u64>().ok().map(|n| (n, id.to_string()))
+        })
+        .max()
+        .map(|(_, id)| id)
+}
+
+/// Load a run log by its ID.
+pub fn load_run_log(paths: &StoragePaths, id: &str) -> Result<RunLog> {
+    let name = format!("{}.json", id);
+    let dirs = runs_dirs(paths);
+    let path = dirs
+        .iter()
+        .map(|dir| dir.join(&name))
+        .find(|path| path.exists())
+        .unwrap_or_else(|| dirs[0].join(&name));
     load_run_log_from_path(&path)
 }
 
@@ -184,6 +536,12 @@ fn load_run_log_from_path(path: &Path) -> Result<RunLog> {
     Ok(log)
 }
 
+/// The findings of the newest review, if it recorded them
+pub fn load_last_run(paths: &StoragePaths) -> Option<LastRun> {
+    let json = std::fs::read_to_string(paths.read_path(LAST_RUN_FILE)).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
 /// Generate a run ID from the current time (millisecond Unix timestamp).
 pub fn new_run_id() -> String {
     SystemTime::now()
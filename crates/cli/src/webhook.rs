@@ -0,0 +1,209 @@
+//! Baseline delta events for `[reporting] webhook_url`
+//!
+//! After a command changes the baseline, the entries added, fixed and
+//! expired per owning team since the last full run recorded on the branch
+//! (see [`revet_core::baseline::delta`]) are POSTed as JSON:
+//!
+//! ```text
+//! POST <webhook_url>
+//! Content-Type: application/json
+//! X-Revet-Event: baseline.delta
+//! X-Revet-Signature-256: sha256=<hex HMAC-SHA256 of the body>
+//! ```
+//!
+//! The HMAC key is the secret in the variable named by
+//! `webhook_secret_env`; without it nothing is sent. A delivery that fails,
+//! or is answered 5xx or 429, is retried `webhook_retries` times, waiting
+//! `webhook_backoff_ms` and doubling the wait each time. Delivery is
+//! best-effort: a failure is a warning, never the command's exit code.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use revet_core::baseline::delta::{Delta, OwnerDelta};
+use revet_core::ReportingConfig;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// `event` of every delta event, and its `X-Revet-Event` header
+pub const EVENT: &str = "baseline.delta";
+
+/// Header carrying the body's signature
+pub const SIGNATURE_HEADER: &str = "X-Revet-Signature-256";
+
+/// The JSON body of a delta event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaEvent {
+    pub event: String,
+    pub revet_version: String,
+    /// Name of the repository's directory
+    pub repository: String,
+    /// Branch checked out, unless HEAD was detached
+    pub branch: Option<String>,
+    /// Command that changed the baseline: `baseline`, `baseline review`
+    /// or `review --fix`
+    pub command: String,
+    /// Unix seconds when the event was made
+    pub timestamp: u64,
+    /// Run ID the changes are counted from; `None` when no full run is
+    /// recorded on the branch
+    pub since: Option<String>,
+    pub total: OwnerDelta,
+    pub owners: Vec<OwnerDelta>,
+}
+
+impl DeltaEvent {
+    pub fn new(
+        repo_root: &Path,
+        branch: Option<String>,
+        command: &str,
+        since: Option<String>,
+        delta: &Delta,
+    ) -> Self {
+        Self {
+            event: EVENT.to_string(),
+            revet_version: revet_core::VERSION.to_string(),
+            repository: repo_root
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            branch,
+            command: command.to_string(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            since,
+            total: delta.total(),
+            owners: delta.owners.clone(),
+        }
+    }
+}
+
+/// HMAC-SHA256 of `message` under `key` (RFC 2104)
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    const BLOCK: usize = 64;
+    let mut block = [0u8; BLOCK];
+    if key.len() > BLOCK {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let pad = |byte: u8| block.iter().map(|b| b ^ byte).collect::<Vec<u8>>();
+    let inner = Sha256::new()
+        .chain_update(pad(0x36))
+        .chain_update(message)
+        .finalize();
+    Sha256::new()
+        .chain_update(pad(0x5c))
+        .chain_update(inner)
+        .finalize()
+        .into()
+}
+
+/// `X-Revet-Signature-256` value for `body`: `sha256=` and the hex HMAC
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mac: String = hmac_sha256(secret.as_bytes(), body)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("sha256={}", mac)
+}
+
+/// POST `body` to `url` with `signature`, retrying as configured. Returns
+/// the number of attempts it took.
+pub fn deliver(config: &ReportingConfig, url: &str, body: &[u8], signature: &str) -> Result<u32> {
+    let client = Client::builder()
+        .user_agent(concat!("revet/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_millis(config.webhook_timeout_ms))
+        .build()
+        .context("Failed to build HTTP client")?;
+    let mut wait = Duration::from_millis(config.webhook_backoff_ms);
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let error = match client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header("X-Revet-Event", EVENT)
+            .header(SIGNATURE_HEADER, signature)
+            .body(body.to_vec())
+            .send()
+        {
+            Ok(resp) if resp.status().is_success() => return Ok(attempt),
+            Ok(resp)
+                if resp.status().is_server_error()
+                    || resp.status() == StatusCode::TOO_MANY_REQUESTS =>
+            {
+                format!("answered {}", resp.status())
+            }
+            Ok(resp) => bail!("answered {}", resp.status()),
+            Err(e) => e.to_string(),
+        };
+        if attempt > config.webhook_retries {
+            bail!("{} ({} attempts)", error, attempt);
+        }
+        std::thread::sleep(wait);
+        wait *= 2;
+    }
+}
+
+/// Send `event` to the configured webhook, or with `dry_run` print it and
+/// its signature to stderr instead. Best-effort: failures are printed.
+pub fn notify(config: &ReportingConfig, event: &DeltaEvent, dry_run: bool) {
+    if config.webhook_url.is_none() && !dry_run {
+        return;
+    }
+    let body = match serde_json::to_vec_pretty(event) {
+        Ok(body) => body,
+        Err(e) => {
+            eprintln!("  warn: baseline delta event not built: {}", e);
+            return;
+        }
+    };
+    let secret = std::env::var(&config.webhook_secret_env)
+        .ok()
+        .filter(|s| !s.is_empty());
+    let signature = secret.as_deref().map(|secret| sign(secret, &body));
+
+    if dry_run {
+        eprintln!(
+            "  {} to {}",
+            "Baseline delta event (dry run, not sent)".bold(),
+            config.webhook_url.as_deref().unwrap_or("(no webhook_url)")
+        );
+        eprintln!(
+            "  {}: {}",
+            SIGNATURE_HEADER,
+            signature.unwrap_or_else(|| format!(
+                "(unsigned: ${} is not set)",
+                config.webhook_secret_env
+            ))
+        );
+        eprintln!("{}", String::from_utf8_lossy(&body));
+        return;
+    }
+
+    let Some(url) = config.webhook_url.as_deref() else {
+        return;
+    };
+    let Some(signature) = signature else {
+        eprintln!(
+            "  warn: baseline delta event not sent: ${} is not set",
+            config.webhook_secret_env
+        );
+        return;
+    };
+    match deliver(config, url, &body, &signature) {
+        Ok(_) => eprintln!(
+            "  {} baseline delta to {} (net {:+})",
+            "Sent".green(),
+            url,
+            event.total.net()
+        ),
+        Err(e) => eprintln!("  warn: baseline delta event not sent: {:#}", e),
+    }
+}
@@ -0,0 +1,122 @@
+//! Helpers shared by the CLI integration tests
+
+#![allow(dead_code)]
+
+use jsonschema::JSONSchema;
+use serde_json::json;
+use std::path::Path;
+use std::process::Command;
+
+/// Run git in `dir` with a fixed committer, asserting it succeeds; its
+/// trimmed stdout
+pub fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=dev", "-c", "user.email=dev@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?}", args);
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+/// The parts of the SARIF 2.1.0 schema a report (or a chunk of one) must
+/// satisfy: the required properties of the log, run, tool, rule and result
+/// objects and the shapes of the fields revet writes
+pub fn sarif_schema_subset() -> JSONSchema {
+    let schema = json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object",
+        "required": ["version", "runs"],
+        "properties": {
+            "$schema": { "type": "string" },
+            "version": { "enum": ["2.1.0"] },
+            "runs": {
+                "type": "array",
+                "minItems": 1,
+                "items": { "$ref": "#/definitions/run" }
+            }
+        },
+        "definitions": {
+            "run": {
+                "type": "object",
+                "required": ["tool"],
+                "properties": {
+                    "tool": {
+                        "type": "object",
+                        "required": ["driver"],
+                        "properties": {
+                            "driver": {
+                                "type": "object",
+                                "required": ["name"],
+                                "properties": {
+                                    "name": { "type": "string" },
+                                    "version": { "type": "string" },
+                                    "rules": {
+                                        "type": "array",
+                                        "items": { "$ref": "#/definitions/rule" }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "results": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/result" }
+                    },
+                    "automationDetails": {
+                        "type": "object",
+                        "properties": { "id": { "type": "string" } }
+                    },
+                    "properties": { "type": "object" }
+                }
+            },
+            "rule": {
+                "type": "object",
+                "required": ["id"],
+                "properties": {
+                    "id": { "type": "string" },
+                    "shortDescription": { "$ref": "#/definitions/message" }
+                }
+            },
+            "message": {
+                "type": "object",
+                "anyOf": [{ "required": ["text"] }, { "required": ["id"] }],
+                "properties": { "text": { "type": "string" } }
+            },
+            "result": {
+                "type": "object",
+                "required": ["message"],
+                "properties": {
+                    "ruleId": { "type": "string" },
+                    "level": { "enum": ["none", "note", "warning", "error"] },
+                    "message": { "$ref": "#/definitions/message" },
+                    "locations": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "physicalLocation": {
+                                    "type": "object",
+                                    "properties": {
+                                        "artifactLocation": {
+                                            "type": "object",
+                                            "properties": { "uri": { "type": "string" } }
+                                        },
+                                        "region": {
+                                            "type": "object",
+                                            "properties": {
+                                                "startLine": { "type": "integer", "minimum": 1 }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    });
+    JSONSchema::compile(&schema).unwrap()
+}
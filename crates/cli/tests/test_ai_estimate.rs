@@ -0,0 +1,420 @@
+use clap::Parser;
+use revet_cli::ai::{AiReasoner, ApiResponse};
+use revet_cli::commands::review;
+use revet_cli::output::json::JsonFinding;
+use revet_cli::Cli;
+use revet_core::ai::{self, estimate_tokens, AiEstimate, AiReport, PriorityContext, SYSTEM_PROMPT};
+use revet_core::config::AIConfig;
+use revet_core::{Finding, Locale, Severity};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+fn cli(args: &[&str]) -> Cli {
+    let mut argv = vec!["revet"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+fn finding(id: &str, severity: Severity, line: usize, suggestion: Option<&str>) -> Finding {
+    Finding {
+        id: id.to_string(),
+        severity,
+        message: format!("{} message", id),
+        file: PathBuf::from("app.py"),
+        line,
+        suggestion: suggestion.map(String::from),
+        ..Default::default()
+    }
+}
+
+/// Two eligible findings, one info and one that already has a suggestion
+fn setup() -> (TempDir, Vec<Finding>) {
+    let dir = TempDir::new().unwrap();
+    let source: String = (1..=20).map(|i| format!("x{} = {}\n", i, i)).collect();
+    std::fs::write(dir.path().join("app.py"), source).unwrap();
+    let findings = vec![
+        finding("SEC-001", Severity::Error, 3, None),
+        finding("ERR-001", Severity::Info, 5, None),
+        finding("ERR-002", Severity::Warning, 10, Some("use ? instead")),
+        finding("SQL-001", Severity::Warning, 15, None),
+    ];
+    (dir, findings)
+}
+
+fn reply(ids: &[&str]) -> ApiResponse {
+    let notes: Vec<_> = ids
+        .iter()
+        .map(|id| serde_json::json!({"id": id, "note": "explained", "false_positive": *id == "SQL-001"}))
+        .collect();
+    ApiResponse {
+        content: serde_json::to_string(&notes).unwrap(),
+        input_tokens: 1000,
+        output_tokens: 100,
+    }
+}
+
+#[test]
+fn test_estimate_matches_mocked_run() {
+    let (dir, mut findings) = setup();
+    let reasoner = AiReasoner::new(AIConfig::default(), Some(10.0));
+    let context = PriorityContext::default();
+    let estimate = reasoner.estimate(&findings, dir.path(), &context).unwrap();
+    let planned = ai::plan(&findings, dir.path(), &AIConfig::default(), 10.0, &context)
+        .unwrap()
+        .batches;
+
+    let mut sent = Vec::new();
+    let stats = reasoner
+        .enrich_with(&mut findings, dir.path(), &context, |batch| {
+            sent.push((batch.system, batch.user.clone()));
+            Ok(reply(&["SEC-001", "SQL-001"]))
+        })
+        .unwrap();
+
+    // The dry run describes exactly the requests that were sent
+    let planned: Vec<_> = planned.iter().map(|b| (b.system, b.user.clone())).collect();
+    assert_eq!(sent, planned);
+    assert_eq!(stats.estimate, estimate);
+    assert_eq!(estimate.requests, sent.len());
+    for ((system, user), batch) in sent.iter().zip(&estimate.batches) {
+        assert_eq!(*system, SYSTEM_PROMPT);
+        assert_eq!(
+            batch.input_tokens,
+            estimate_tokens(system) + estimate_tokens(user)
+        );
+    }
+
+    assert_eq!(estimate.eligible, 2);
+    assert_eq!(estimate.output_tokens, 160);
+    assert!(sent[0].1.contains("SEC-001") && sent[0].1.contains("SQL-001"));
+    assert!(!sent[0].1.contains("ERR-"));
+    assert_eq!(stats.findings_enriched, 2);
+    assert_eq!(stats.false_positives, 1);
+    assert!(findings[3].ai_false_positive);
+    assert_eq!(findings[1].ai_note, None);
+    assert!(stats.cost_usd > 0.0);
+}
+
+#[test]
+fn test_zero_budget_makes_no_requests() {
+    let (dir, mut findings) = setup();
+    let reasoner = AiReasoner::new(AIConfig::default(), Some(0.0));
+    let context = PriorityContext::default();
+    let estimate = reasoner.estimate(&findings, dir.path(), &context).unwrap();
+    assert!(estimate.exceeds_max_cost);
+    assert_eq!(estimate.requests, 0);
+    assert_eq!(estimate.skipped_budget, 2);
+
+    let mut calls = 0;
+    let stats = reasoner
+        .enrich_with(&mut findings, dir.path(), &context, |_| {
+            calls += 1;
+            Ok(reply(&[]))
+        })
+        .unwrap();
+    assert_eq!(calls, 0);
+    assert_eq!(stats.skipped_budget, 2);
+    assert_eq!(stats.score_cutoff, None);
+    assert_eq!(findings[0].ai_skipped.as_deref(), Some("budget"));
+    assert_eq!(findings[1].ai_skipped, None);
+}
+
+/// More eligible findings than the budget covers: six low-priority
+/// warnings in test code around two errors in `payments/`
+fn over_budget() -> (TempDir, Vec<Finding>) {
+    let dir = TempDir::new().unwrap();
+    let source: String = (1..=20).map(|i| format!("x{} = {}\n", i, i)).collect();
+    for file in ["payments/charge.py", "tests/helpers.py"] {
+        let path = dir.path().join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, &source).unwrap();
+    }
+    let mut findings: Vec<Finding> = (1..=6)
+        .map(|i| Finding {
+            file: dir.path().join("tests/helpers.py"),
+            confidence: revet_core::Confidence::Medium,
+            ..finding(&format!("ERR-{:03}", i), Severity::Warning, i * 2, None)
+        })
+        .collect();
+    for (i, id) in [(3, "SQL-001"), (5, "SEC-001")] {
+        findings.insert(
+            i,
+            Finding {
+                file: dir.path().join("payments/charge.py"),
+                ..finding(id, Severity::Error, i + 4, None)
+            },
+        );
+    }
+    (dir, findings)
+}
+
+#[test]
+fn test_budget_goes_to_highest_priority() {
+    let (dir, mut findings) = over_budget();
+    let critical = vec!["payments/**".to_string()];
+    let context = PriorityContext {
+        critical_paths: &critical,
+        ..Default::default()
+    };
+
+    // Price the two errors alone, then give the whole set that budget
+    let errors: Vec<Finding> = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Error)
+        .cloned()
+        .collect();
+    let config = AIConfig::default();
+    let plan = ai::plan(&errors, dir.path(), &config, 10.0, &context).unwrap();
+    let budget = AiEstimate::new(&plan, &config, 10.0).estimated_cost;
+
+    let reasoner = AiReasoner::new(config, Some(budget));
+    let mut sent = Vec::new();
+    let stats = reasoner
+        .enrich_with(&mut findings, dir.path(), &context, |batch| {
+            sent.push(batch.findings.len());
+            Ok(reply(&["SQL-001", "SEC-001"]))
+        })
+        .unwrap();
+    assert_eq!(sent, [2]);
+    assert_eq!(stats.findings_enriched, 2);
+    assert_eq!(stats.skipped_budget, 6);
+    assert_eq!(stats.score_cutoff, Some(90.0));
+    assert!(stats.estimate.estimated_cost <= budget);
+
+    let json: Vec<serde_json::Value> = findings
+        .iter()
+        .map(|f| serde_json::to_value(JsonFinding::new(f, Locale::En)).unwrap())
+        .collect();
+    for (f, json) in findings.iter().zip(&json) {
+        if f.severity == Severity::Error {
+            assert_eq!(f.ai_note.as_deref(), Some("explained"), "{}", f.id);
+            assert!(json.get("skipped_reason").is_none(), "{}", f.id);
+        } else {
+            assert_eq!(f.ai_note, None, "{}", f.id);
+            assert_eq!(json["skipped_reason"], "budget", "{}", f.id);
+        }
+    }
+
+    // Reruns, in any order, enrich the same findings
+    let (dir, mut reordered) = over_budget();
+    reordered.reverse();
+    let all: Vec<String> = reordered.iter().map(|f| f.id.clone()).collect();
+    let all: Vec<&str> = all.iter().map(String::as_str).collect();
+    reasoner
+        .enrich_with(&mut reordered, dir.path(), &context, |_| Ok(reply(&all)))
+        .unwrap();
+    let enriched: Vec<&str> = reordered
+        .iter()
+        .filter(|f| f.ai_note.is_some())
+        .map(|f| f.id.as_str())
+        .collect();
+    assert_eq!(enriched, ["SEC-001", "SQL-001"]);
+}
+
+#[test]
+fn test_review_shortcuts() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join(".revet.toml"),
+        r#"
+[[rules]]
+id       = "no-eval"
+pattern  = 'eval\('
+message  = "eval() runs arbitrary code"
+severity = "error"
+paths    = ["*.py"]
+
+[[rules]]
+id       = "no-print"
+pattern  = 'print\('
+message  = "print() left in code"
+severity = "warning"
+paths    = ["*.py"]
+"#,
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("app.py"), "eval(x)\nprint(x)\n").unwrap();
+    let eligible = |args: &[&str]| {
+        let mut argv = vec!["--full", "--ai", "--estimate-only"];
+        argv.extend_from_slice(args);
+        let run = review::analyze(dir.path(), &cli(&argv)).unwrap();
+        run.ai.unwrap().estimate.eligible
+    };
+
+    assert_eq!(eligible(&[]), 2);
+    assert_eq!(eligible(&["--ai-min-severity", "error"]), 1);
+    assert_eq!(eligible(&["--ai-only-new"]), 2);
+
+    // The print() finding is baselined
+    let run = review::analyze(dir.path(), &cli(&["--full"])).unwrap();
+    let old: Vec<Finding> = run
+        .findings
+        .into_iter()
+        .filter(|f| f.severity == Severity::Warning)
+        .collect();
+    revet_core::Baseline::from_findings(&old, dir.path(), None)
+        .save(
+            dir.path(),
+            &mut revet_cli::commands::journal(
+                dir.path(),
+                &revet_core::StoragePaths::in_repo(dir.path()),
+                false,
+            ),
+        )
+        .unwrap();
+    assert_eq!(eligible(&["--ai-only-new"]), 1);
+    assert_eq!(
+        eligible(&["--ai-only-new", "--ai-min-severity", "error"]),
+        1
+    );
+
+    assert!(Cli::try_parse_from(["revet", "--ai-min-severity", "info"]).is_err());
+}
+
+#[test]
+fn test_estimate_lists_provider_cost() {
+    let (dir, findings) = setup();
+    let ollama = AIConfig {
+        provider: "ollama".to_string(),
+        model: "llama3".to_string(),
+        ..AIConfig::default()
+    };
+    let estimate = AiReasoner::new(ollama, None)
+        .estimate(&findings, dir.path(), &PriorityContext::default())
+        .unwrap();
+    assert_eq!(estimate.providers.len(), 1);
+    assert_eq!(estimate.providers[0].provider, "ollama");
+    assert_eq!(estimate.estimated_cost, 0.0);
+    assert_eq!(estimate.max_cost, AIConfig::default().max_cost_per_run);
+    assert!(!estimate.exceeds_max_cost);
+}
+
+#[test]
+fn test_no_eligible_findings() {
+    let (dir, mut findings) = setup();
+    findings.retain(|f| !ai::is_eligible(f));
+    let reasoner = AiReasoner::new(AIConfig::default(), None);
+    let context = PriorityContext::default();
+    let estimate = reasoner.estimate(&findings, dir.path(), &context).unwrap();
+    assert_eq!(estimate.requests, 0);
+    assert_eq!(estimate.estimated_cost, 0.0);
+
+    let stats = reasoner
+        .enrich_with(&mut findings, dir.path(), &context, |_| {
+            panic!("no request expected")
+        })
+        .unwrap();
+    assert_eq!(stats.findings_enriched, 0);
+}
+
+#[test]
+fn test_report_json() {
+    let (dir, findings) = setup();
+    let estimate = AiReasoner::new(AIConfig::default(), None)
+        .estimate(&findings, dir.path(), &PriorityContext::default())
+        .unwrap();
+
+    let dry = serde_json::to_value(AiReport {
+        estimate: estimate.clone(),
+        actual_cost: None,
+    })
+    .unwrap();
+    assert_eq!(dry["requests"], 1);
+    assert_eq!(dry["estimated_cost"], estimate.estimated_cost);
+    assert!(dry.get("actual_cost").is_none());
+
+    let real = serde_json::to_value(AiReport {
+        estimate,
+        actual_cost: Some(0.0123),
+    })
+    .unwrap();
+    assert_eq!(real["actual_cost"], 0.0123);
+    assert_eq!(real["batches"][0]["findings"], 2);
+}
+
+#[test]
+fn test_estimate_only_review() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("app.py"),
+        "import os\nos.system(\"rm -rf \" + user_input)\n",
+    )
+    .unwrap();
+
+    let run = review::analyze(
+        dir.path(),
+        &cli(&["--full", "--ai", "--estimate-only", "--max-cost", "5"]),
+    )
+    .unwrap();
+    let report = run.ai.unwrap();
+    assert_eq!(report.actual_cost, None);
+    assert_eq!(report.estimate.max_cost, 5.0);
+    assert!(run.findings.iter().all(|f| f.ai_note.is_none()));
+
+    let err = review::analyze(dir.path(), &cli(&["--full", "--estimate-only"]))
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("--estimate-only needs --ai"));
+}
+
+#[test]
+fn test_pipeline_estimate_matches_review() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join(".revet.toml"),
+        r#"
+[[rules]]
+id       = "no-eval"
+pattern  = 'eval\('
+message  = "eval() runs arbitrary code"
+severity = "error"
+paths    = ["*.py"]
+"#,
+    )
+    .unwrap();
+    // Two equal findings; only the graph tells the widely called one apart
+    std::fs::write(dir.path().join("leaf.py"), "def leaf(x):\n    eval(x)\n").unwrap();
+    std::fs::write(dir.path().join("zhub.py"), "def hub(x):\n    eval(x)\n").unwrap();
+    for i in 0..4 {
+        std::fs::write(
+            dir.path().join(format!("caller{}.py", i)),
+            format!("from zhub import hub\n\ndef call{}(x):\n    hub(x)\n", i),
+        )
+        .unwrap();
+    }
+
+    let config = revet_core::RevetConfig::find_and_load(dir.path()).unwrap();
+    let analysis = revet_core::analyze_root(dir.path(), &config).unwrap();
+    let one: Vec<Finding> = analysis
+        .findings
+        .iter()
+        .filter(|f| f.file.ends_with("leaf.py"))
+        .cloned()
+        .collect();
+    // Sized with the graph, which puts the enclosing function in the prompt
+    let with_graph = PriorityContext {
+        graph: Some(&analysis.graph),
+        ..PriorityContext::default()
+    };
+    let plan = ai::plan(&one, &analysis.root, &config.ai, 10.0, &with_graph).unwrap();
+    let budget = AiEstimate::new(&plan, &config.ai, 10.0).estimated_cost;
+
+    let estimate = analysis.ai_estimate(&config, budget).unwrap();
+    let max_cost = budget.to_string();
+    let args = ["--full", "--ai", "--estimate-only", "--max-cost", &max_cost];
+    let run = review::analyze(dir.path(), &cli(&args)).unwrap();
+    assert_eq!(run.ai.unwrap().estimate, estimate);
+
+    // Without the graph, the tie would be broken by file name instead
+    let plan = ai::plan(
+        &analysis.findings,
+        &analysis.root,
+        &config.ai,
+        budget,
+        &PriorityContext::default(),
+    )
+    .unwrap();
+    let blind = AiEstimate::new(&plan, &config.ai, budget);
+    assert_eq!(estimate.skipped_budget, 1);
+    assert!(estimate.score_cutoff > blind.score_cutoff, "{:?}", estimate);
+}
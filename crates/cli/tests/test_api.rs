@@ -0,0 +1,495 @@
+#![cfg(feature = "api")]
+
+use clap::Parser;
+use jsonschema::{Draft, JSONSchema};
+use revet_cli::commands::api::{ApiServer, ApiState};
+use revet_cli::commands::review;
+use revet_cli::Cli;
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use tempfile::TempDir;
+
+fn cli(args: &[&str]) -> Cli {
+    let mut argv = vec!["revet"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+fn copy_dir(src: &Path, dst: &Path) {
+    std::fs::create_dir_all(dst).unwrap();
+    for entry in std::fs::read_dir(src).unwrap() {
+        let path = entry.unwrap().path();
+        let target = dst.join(path.file_name().unwrap());
+        if path.is_dir() {
+            copy_dir(&path, &target);
+        } else {
+            std::fs::copy(&path, &target).unwrap();
+        }
+    }
+}
+
+/// A copy of the `api_dashboard` fixture at `dir`, reviewed with `args`
+fn reviewed_fixture(dir: &Path, args: &[&str]) {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/api_dashboard");
+    copy_dir(&src, dir);
+    review::analyze(dir, &cli(args)).unwrap();
+}
+
+/// A running API server, stopped on drop
+struct Server {
+    server: Arc<ApiServer>,
+    addr: SocketAddr,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Server {
+    fn start(path: &Path) -> Self {
+        let state = ApiState::open(path).unwrap();
+        let server = Arc::new(ApiServer::bind("127.0.0.1:0".parse().unwrap(), false).unwrap());
+        let addr = server.local_addr().unwrap();
+        let serving = server.clone();
+        let thread = std::thread::spawn(move || serving.serve(&state));
+        Self {
+            server,
+            addr,
+            thread: Some(thread),
+        }
+    }
+
+    fn request(&self, path: &str, if_none_match: Option<&str>) -> Response {
+        let mut request =
+            reqwest::blocking::Client::new().get(format!("http://{}{}", self.addr, path));
+        if let Some(etag) = if_none_match {
+            request = request.header("If-None-Match", etag);
+        }
+        let response = request.send().unwrap();
+        let status = response.status().as_u16();
+        let etag = response
+            .headers()
+            .get("etag")
+            .map(|v| v.to_str().unwrap().to_string());
+        let text = response.text().unwrap();
+        let body = if text.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(&text).unwrap()
+        };
+        Response { status, etag, body }
+    }
+
+    fn get(&self, path: &str) -> Response {
+        self.request(path, None)
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.server.shutdown();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    etag: Option<String>,
+    body: Value,
+}
+
+impl Response {
+    fn error(&self) -> &str {
+        self.body["error"].as_str().unwrap_or_default()
+    }
+}
+
+/// `(id prefix, file)` of each finding in a `/findings` body
+fn finding_rows(body: &Value) -> Vec<(String, String)> {
+    body["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| {
+            let id = f["id"].as_str().unwrap();
+            let prefix = id.rsplit_once('-').unwrap().0.to_string();
+            (prefix, f["file"].as_str().unwrap().to_string())
+        })
+        .collect()
+}
+
+fn api_fixture() -> (TempDir, Server) {
+    let dir = TempDir::new().unwrap();
+    reviewed_fixture(dir.path(), &["--full", "--coverage-report"]);
+    let server = Server::start(dir.path());
+    (dir, server)
+}
+
+// ── /findings ────────────────────────────────────────────────────
+
+#[test]
+fn test_findings_and_filters() {
+    let (dir, server) = api_fixture();
+    let service = ("SEC".to_string(), "app/service.py".to_string());
+    let views = ("ERR".to_string(), "app/views.py".to_string());
+
+    let all = server.get("/findings");
+    assert_eq!(all.status, 200);
+    assert_eq!(
+        finding_rows(&all.body),
+        vec![service.clone(), views.clone()]
+    );
+    assert!(all.body["findings"][0].get("repo").is_none());
+
+    for (query, expected) in [
+        ("severity=error", vec![service.clone()]),
+        ("severity=warning,info", vec![views.clone()]),
+        ("rule=ERR", vec![views.clone()]),
+        ("rule=error_handling", vec![views.clone()]),
+        ("rule=security%3Asecrets", vec![service.clone()]),
+        ("rule=security:bare_except", vec![]),
+        ("path=app/views.py", vec![views.clone()]),
+        ("path=app", vec![service.clone(), views.clone()]),
+        ("path=ap", vec![]),
+        ("severity=error&path=app/views.py", vec![]),
+    ] {
+        let response = server.get(&format!("/findings?{}", query));
+        assert_eq!(response.status, 200, "{}", query);
+        assert_eq!(finding_rows(&response.body), expected, "{}", query);
+    }
+
+    let name = dir.path().file_name().unwrap().to_str().unwrap();
+    let response = server.get(&format!("/findings?project={}", name));
+    assert_eq!(finding_rows(&response.body).len(), 2);
+}
+
+#[test]
+fn test_findings_validation() {
+    let (_dir, server) = api_fixture();
+
+    let response = server.get("/findings?severity=fatal");
+    assert_eq!(response.status, 400);
+    assert_eq!(
+        response.error(),
+        "severity = \"fatal\" is invalid. Must be one of: error, warning, info"
+    );
+
+    let response = server.get("/findings?sevrity=error");
+    assert_eq!(response.status, 400);
+    assert!(response
+        .error()
+        .starts_with("unknown query parameter `sevrity`"));
+
+    let response = server.get("/findings?rule=SEC&rule=ERR");
+    assert_eq!(response.status, 400);
+
+    let response = server.get("/findings?path=%zz");
+    assert_eq!(response.status, 400);
+
+    let response = server.get("/findings?project=nope");
+    assert_eq!(response.status, 404);
+    assert_eq!(response.error(), "no project named `nope`");
+}
+
+// ── /summary ─────────────────────────────────────────────────────
+
+#[test]
+fn test_summary() {
+    let (dir, server) = api_fixture();
+    let response = server.get("/summary");
+    assert_eq!(response.status, 200);
+    let body = &response.body;
+    assert_eq!(body["summary"]["errors"], 1);
+    assert_eq!(body["summary"]["warnings"], 1);
+    assert_eq!(body["summary"]["info"], 0);
+
+    let project = &body["projects"][0];
+    assert_eq!(
+        project["project"],
+        dir.path().file_name().unwrap().to_str().unwrap()
+    );
+    assert_eq!(project["files_analyzed"], 4);
+    assert_eq!(
+        response.etag,
+        Some(format!("\"{}\"", project["run_id"].as_str().unwrap()))
+    );
+}
+
+// ── /graph ───────────────────────────────────────────────────────
+
+#[test]
+fn test_graph_node() {
+    let (_dir, server) = api_fixture();
+
+    let response = server.get("/graph/node?file=app/models.py&name=load_user");
+    assert_eq!(response.status, 200);
+    let nodes = response.body["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0]["kind"], "Function");
+    assert_eq!(nodes[0]["file"], "app/models.py");
+    assert_eq!(nodes[0]["line"], 7);
+
+    let response = server.get("/graph/node?file=app/models.py");
+    let names: Vec<&str> = response.body["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n["name"].as_str().unwrap())
+        .collect();
+    assert!(names.contains(&"User") && names.contains(&"load_user"));
+
+    let response = server.get("/graph/node?file=app/models.py&name=save_user");
+    assert_eq!(response.status, 404);
+
+    let response = server.get("/graph/node?name=load_user");
+    assert_eq!(response.status, 400);
+    assert_eq!(response.error(), "query parameter `file` is required");
+
+    let response = server.get("/graph/node?file=../models.py");
+    assert_eq!(response.status, 400);
+}
+
+#[test]
+fn test_graph_dependents() {
+    let (_dir, server) = api_fixture();
+
+    let response = server.get("/graph/dependents?file=app/models.py");
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body["transitive"], false);
+    assert_eq!(
+        response.body["dependents"],
+        serde_json::json!(["app/service.py"])
+    );
+
+    let response = server.get("/graph/dependents?file=app/models.py&transitive=true");
+    assert_eq!(
+        response.body["dependents"],
+        serde_json::json!(["app/service.py", "app/views.py"])
+    );
+
+    let response = server.get("/graph/dependents?file=app/views.py");
+    assert_eq!(response.body["dependents"], serde_json::json!([]));
+
+    let response = server.get("/graph/dependents?file=app/models.py&transitive=yes");
+    assert_eq!(response.status, 400);
+
+    let response = server.get("/graph/dependents?file=app/missing.py");
+    assert_eq!(response.status, 404);
+}
+
+// ── /coverage ────────────────────────────────────────────────────
+
+#[test]
+fn test_coverage() {
+    let (_dir, server) = api_fixture();
+    let response = server.get("/coverage");
+    assert_eq!(response.status, 200);
+    // README.md is discovered but not analyzed
+    assert_eq!(response.body["discovered"], 5);
+    assert_eq!(response.body["analyzed"], 4);
+    assert_eq!(
+        response.body["skipped"][0]["reason"],
+        "unsupported_extension"
+    );
+
+    // Not recorded without --coverage-report
+    let dir = TempDir::new().unwrap();
+    reviewed_fixture(dir.path(), &["--full"]);
+    let server = Server::start(dir.path());
+    let response = server.get("/coverage");
+    assert_eq!(response.status, 404);
+    assert!(response.error().contains("--coverage-report"));
+}
+
+// ── Caching and runs ─────────────────────────────────────────────
+
+#[test]
+fn test_etag_follows_the_last_run() {
+    let (dir, server) = api_fixture();
+    let first = server.get("/findings");
+    let etag = first.etag.clone().unwrap();
+
+    let cached = server.request("/findings", Some(&etag));
+    assert_eq!(cached.status, 304);
+    assert_eq!(cached.etag.as_deref(), Some(etag.as_str()));
+    assert_eq!(cached.body, Value::Null);
+    assert_eq!(server.request("/summary", Some("*")).status, 304);
+
+    // A new review replaces the data without restarting the server
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    std::fs::write(dir.path().join("app/views.py"), "VALUE = 1\n").unwrap();
+    review::analyze(dir.path(), &cli(&["--full"])).unwrap();
+
+    let fresh = server.request("/findings", Some(&etag));
+    assert_eq!(fresh.status, 200);
+    assert_ne!(fresh.etag, Some(etag));
+    assert_eq!(finding_rows(&fresh.body).len(), 1);
+}
+
+#[test]
+fn test_no_run_yet() {
+    let dir = TempDir::new().unwrap();
+    let server = Server::start(dir.path());
+    for path in [
+        "/findings",
+        "/summary",
+        "/coverage",
+        "/graph/node?file=a.py",
+    ] {
+        let response = server.get(path);
+        assert_eq!(response.status, 404, "{}", path);
+        assert!(
+            response.error().contains("no review run recorded"),
+            "{}",
+            path
+        );
+    }
+}
+
+#[test]
+fn test_unknown_route_and_method() {
+    let (_dir, server) = api_fixture();
+    assert_eq!(server.get("/graph").status, 404);
+    assert_eq!(server.get("/findings/SEC-001").status, 404);
+
+    let response = reqwest::blocking::Client::new()
+        .post(format!("http://{}/findings", server.addr))
+        .send()
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 405);
+}
+
+// ── Workspaces ───────────────────────────────────────────────────
+
+#[test]
+fn test_workspace_projects() {
+    let dir = TempDir::new().unwrap();
+    reviewed_fixture(&dir.path().join("billing"), &["--full"]);
+    reviewed_fixture(&dir.path().join("accounts"), &["--full"]);
+    std::fs::write(
+        dir.path().join("revet-workspace.toml"),
+        "repos = [\"billing\", \"accounts\"]\n",
+    )
+    .unwrap();
+    let server = Server::start(dir.path());
+
+    let response = server.get("/findings?severity=error");
+    let repos: Vec<&str> = response.body["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["repo"].as_str().unwrap())
+        .collect();
+    assert_eq!(repos, vec!["billing", "accounts"]);
+    assert!(response.etag.unwrap().contains('+'));
+
+    let response = server.get("/findings?project=accounts");
+    assert_eq!(finding_rows(&response.body).len(), 2);
+
+    let response = server.get("/summary");
+    assert_eq!(response.body["summary"]["errors"], 2);
+    assert_eq!(response.body["projects"].as_array().unwrap().len(), 2);
+
+    let response = server.get("/graph/node?file=app/models.py");
+    assert_eq!(response.status, 400);
+    assert_eq!(
+        response.error(),
+        "`project` is required in a workspace (one of: billing, accounts)"
+    );
+    let response = server.get("/graph/node?file=app/models.py&project=billing");
+    assert_eq!(response.status, 200);
+}
+
+// ── Binding ──────────────────────────────────────────────────────
+
+#[test]
+fn test_bind_is_loopback_unless_allowed() {
+    let remote: SocketAddr = "0.0.0.0:0".parse().unwrap();
+    let err = ApiServer::bind(remote, false).err().unwrap();
+    assert!(err.to_string().contains("--allow-remote"));
+    assert!(ApiServer::bind(remote, true).is_ok());
+
+    let cli = cli(&["api"]);
+    let Some(revet_cli::Commands::Api {
+        bind, allow_remote, ..
+    }) = cli.command
+    else {
+        panic!("expected the api command");
+    };
+    assert!(bind.ip().is_loopback());
+    assert!(!allow_remote);
+}
+
+// ── OpenAPI ──────────────────────────────────────────────────────
+
+/// Validate `value` against a JSON Schema of the OpenAPI document
+fn check(schema: &Value, value: &Value, at: &str) {
+    let schema = JSONSchema::options()
+        .with_draft(Draft::Draft202012)
+        .compile(schema)
+        .unwrap_or_else(|e| panic!("{}: invalid schema: {}", at, e));
+    if let Err(errors) = schema.validate(value) {
+        let errors: Vec<String> = errors
+            .map(|e| format!("{} at {}", e, e.instance_path))
+            .collect();
+        panic!("{}: {}", at, errors.join("; "));
+    };
+}
+
+#[test]
+fn test_openapi_describes_every_response() {
+    let dir = TempDir::new().unwrap();
+    reviewed_fixture(dir.path(), &["--full", "--coverage-report=full"]);
+    let server = Server::start(dir.path());
+
+    let spec = server.get("/openapi.json");
+    assert_eq!(spec.status, 200);
+    assert_eq!(spec.body["openapi"], "3.1.0");
+    let paths = spec.body["paths"].as_object().unwrap();
+    let mut routes: Vec<&str> = paths.keys().map(String::as_str).collect();
+    routes.sort();
+    assert_eq!(
+        routes,
+        vec![
+            "/coverage",
+            "/findings",
+            "/graph/dependents",
+            "/graph/node",
+            "/openapi.json",
+            "/summary"
+        ]
+    );
+
+    for (url, route) in [
+        ("/findings", "/findings"),
+        ("/summary", "/summary"),
+        ("/graph/node?file=app/models.py", "/graph/node"),
+        (
+            "/graph/dependents?file=app/models.py&transitive=true",
+            "/graph/dependents",
+        ),
+        ("/coverage", "/coverage"),
+    ] {
+        let response = server.get(url);
+        assert_eq!(response.status, 200, "{}", url);
+        let schema =
+            &paths[route]["get"]["responses"]["200"]["content"]["application/json"]["schema"];
+        check(schema, &response.body, url);
+    }
+
+    let error = server.get("/findings?severity=fatal");
+    let schema =
+        &paths["/findings"]["get"]["responses"]["400"]["content"]["application/json"]["schema"];
+    check(schema, &error.body, "400");
+
+    let params: Vec<&str> = paths["/graph/dependents"]["get"]["parameters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(params, vec!["file", "transitive", "project"]);
+}
@@ -0,0 +1,125 @@
+//! `revet audit`: `--fix` runs logged, `--no-audit` gaps, runs that change
+//! nothing leave no entry, and `verify` failing on a tampered log
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+mod common;
+use common::git;
+
+const CONFIG: &str = "[modules]\ndebug_artifacts = true\n";
+const DEBUGGER: &str = "def charge(amount):\n    breakpoint()\n    return amount\n";
+
+/// A committed git repository with a debugger statement to fix
+fn repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join(".revet.toml"), CONFIG).unwrap();
+    fs::write(dir.path().join("billing.py"), DEBUGGER).unwrap();
+    git(dir.path(), &["init", "-q"]);
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-q", "-m", "base"]);
+    dir
+}
+
+fn revet(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(args)
+        .current_dir(dir)
+        .env_remove("REVET_DATA_DIR")
+        .env_remove("GITHUB_ACTIONS")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn entries(dir: &Path) -> Vec<serde_json::Value> {
+    let output = revet(dir, &["audit", "show", "--format", "json"]);
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn log(dir: &Path) -> std::path::PathBuf {
+    dir.join(".revet-cache/audit.log.jsonl")
+}
+
+#[test]
+fn test_fix_is_logged_and_verifies() {
+    let dir = repo();
+    revet(dir.path(), &["--full", "--fix", "--fail-on", "never"]);
+    assert!(!fs::read_to_string(dir.path().join("billing.py"))
+        .unwrap()
+        .contains("breakpoint()"));
+
+    let entries = entries(dir.path());
+    assert_eq!(entries.len(), 1, "{:?}", entries);
+    let entry = &entries[0];
+    assert_eq!(entry["seq"], 1);
+    assert!(entry["command"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|a| a == "--fix"));
+    let file = &entry["files"][0];
+    assert_eq!(file["path"], "billing.py");
+    assert!(file["findings"][0].as_str().unwrap().starts_with("DBG-"));
+    assert_eq!(file["ranges"].as_array().unwrap().len(), 1);
+
+    let verify = revet(dir.path(), &["audit", "verify"]);
+    assert_eq!(verify.status.code(), Some(0));
+
+    // Since a date after the entry's, nothing
+    let later = revet(
+        dir.path(),
+        &["audit", "show", "--since", "9999-01-01", "--format", "json"],
+    );
+    assert_eq!(String::from_utf8_lossy(&later.stdout).trim(), "[]");
+}
+
+#[test]
+fn test_tampered_log_fails_verify() {
+    let dir = repo();
+    revet(dir.path(), &["--full", "--fix", "--fail-on", "never"]);
+    let content = fs::read_to_string(log(dir.path())).unwrap();
+    fs::write(log(dir.path()), content.replace("billing.py", "other.py")).unwrap();
+
+    let verify = revet(dir.path(), &["audit", "verify", "--print-exit-reason"]);
+    assert_eq!(verify.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&verify.stderr);
+    assert!(stderr.contains("entry #1 was modified"), "{}", stderr);
+    assert!(
+        stderr.contains("reason=audit_tampered problems=1"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_runs_without_changes_leave_no_entry() {
+    let dir = repo();
+    revet(dir.path(), &["--full", "--fail-on", "never"]);
+    revet(dir.path(), &["baseline", "review", "--report"]);
+    assert!(!log(dir.path()).exists());
+    assert!(entries(dir.path()).is_empty());
+}
+
+#[test]
+fn test_no_audit_run_is_noted_as_a_gap() {
+    let dir = repo();
+    revet(
+        dir.path(),
+        &["--full", "--fix", "--no-audit", "--fail-on", "never"],
+    );
+    assert!(!fs::read_to_string(dir.path().join("billing.py"))
+        .unwrap()
+        .contains("breakpoint()"));
+    assert!(!log(dir.path()).exists());
+
+    // The next logged change notes the gap
+    revet(dir.path(), &["baseline"]);
+    let entries = entries(dir.path());
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["gap"]["commands"], 1);
+    assert_eq!(entries[0]["files"][0]["path"], ".revet-cache/baseline.json");
+}
@@ -0,0 +1,227 @@
+//! `revet baseline review`: the interactive session driven from a scripted
+//! input, applying its decisions, and the non-interactive `--report`
+
+use revet_cli::commands::baseline::{apply, session, summarize, Decision, Review};
+use revet_cli::commands::journal;
+use revet_core::{entry_statuses, Baseline, Finding, Severity, StoragePaths};
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+const SQL_INJECTION: &str =
+    "def charge(cursor, user_id):\n    cursor.execute(\"SELECT * FROM users WHERE id = \" + user_id)\n";
+
+fn write(dir: &Path, name: &str, content: &str) {
+    let path = dir.join(name);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn finding(dir: &Path, file: &str, rule: &str, line: usize) -> Finding {
+    Finding {
+        id: "SEC-001".to_string(),
+        severity: Severity::Error,
+        message: format!("{} in {}", rule, file),
+        file: dir.join(file),
+        line,
+        rule: Some(rule.to_string()),
+        ..Default::default()
+    }
+}
+
+/// Three source files and a baseline of one finding in each
+fn setup() -> (TempDir, Vec<Finding>, Baseline) {
+    let dir = TempDir::new().unwrap();
+    let source = "def run():\n    x = 1\n    return x\n";
+    for file in ["legacy/a.py", "src/b.py", "src/c.py"] {
+        write(dir.path(), file, source);
+    }
+    let findings = vec![
+        finding(dir.path(), "legacy/a.py", "security:secrets", 2),
+        finding(dir.path(), "src/b.py", "security:secrets", 2),
+        finding(dir.path(), "src/c.py", "security:sql_injection", 2),
+    ];
+    let baseline = Baseline::from_findings(&findings, dir.path(), None);
+    (dir, findings, baseline)
+}
+
+fn run_session(
+    dir: &Path,
+    findings: &[Finding],
+    baseline: &Baseline,
+    input: &str,
+) -> Vec<Decision> {
+    let statuses = entry_statuses(baseline, findings, dir);
+    let review = Review {
+        baseline,
+        statuses: &statuses,
+        findings,
+        repo_root: dir,
+    };
+    let mut out = Vec::new();
+    session(&review, &mut Cursor::new(input), &mut out).unwrap()
+}
+
+#[test]
+fn test_batch_actions_limited_to_a_path() {
+    let (dir, findings, baseline) = setup();
+
+    // Expire `legacy/`, then date what is left of the group
+    let decisions = run_session(
+        dir.path(),
+        &findings,
+        &baseline,
+        "x legacy/\nd\nnot-a-date\n2000-01-01\n2099-01-31\nk\n",
+    );
+    assert_eq!(
+        decisions,
+        vec![
+            Decision::Expire,
+            Decision::ExpireOn("2099-01-31".to_string()),
+            Decision::Keep,
+        ]
+    );
+}
+
+#[test]
+fn test_review_each_entry_and_apply() {
+    let (dir, findings, baseline) = setup();
+    let paths = StoragePaths::discover(dir.path());
+    baseline
+        .save_at(&paths, &mut journal(dir.path(), &paths, false))
+        .unwrap();
+
+    // Keep the secrets group; in the other, an empty reason is asked again
+    let decisions = run_session(
+        dir.path(),
+        &findings,
+        &baseline,
+        "k\nr\ns\n\nparameterised upstream\n",
+    );
+    assert_eq!(
+        decisions[2],
+        Decision::Suppress("parameterised upstream".to_string())
+    );
+
+    let statuses = entry_statuses(&baseline, &findings, dir.path());
+    let review = Review {
+        baseline: &baseline,
+        statuses: &statuses,
+        findings: &findings,
+        repo_root: dir.path(),
+    };
+    let mut out = Vec::new();
+    assert!(summarize(&review, &decisions, &mut out).unwrap());
+    let summary = String::from_utf8_lossy(&out);
+    assert!(summary.contains("0 expired, 0 given an expiry date, 1 suppressed inline"));
+
+    let mut journal = journal(dir.path(), &paths, false);
+    apply(&review, &decisions, &paths, &mut journal).unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("src/c.py")).unwrap(),
+        "def run():\n    # revet-ignore security:sql_injection — parameterised upstream\n    x = 1\n    return x\n"
+    );
+    let saved = Baseline::load_at(&paths).unwrap().unwrap();
+    assert_eq!(saved.count, 2);
+    assert!(saved.entries.iter().all(|e| e.file != "src/c.py"));
+
+    // Both writes are in one audit entry, the directive with its finding
+    let entry = journal.finish().unwrap().unwrap();
+    assert_eq!(entry.seq, 2);
+    let changed: Vec<&str> = entry.files.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(changed, ["src/c.py", ".revet-cache/baseline.json"]);
+    assert_eq!(entry.files[0].findings.len(), 1);
+    assert_eq!(entry.files[0].ranges.len(), 1);
+}
+
+#[test]
+fn test_quitting_keeps_the_rest() {
+    let (dir, findings, baseline) = setup();
+
+    let decisions = run_session(dir.path(), &findings, &baseline, "r\nx\nq\n");
+    assert_eq!(
+        decisions,
+        vec![Decision::Expire, Decision::Keep, Decision::Keep]
+    );
+
+    // End of input quits too, and nothing is left to apply
+    let decisions = run_session(dir.path(), &findings, &baseline, "");
+    assert!(decisions.iter().all(|d| *d == Decision::Keep));
+}
+
+#[test]
+fn test_entries_without_a_finding_cannot_be_suppressed() {
+    let (dir, findings, baseline) = setup();
+    fs::remove_file(dir.path().join("legacy/a.py")).unwrap();
+
+    let decisions = run_session(dir.path(), &findings[1..], &baseline, "r\ns\nx\nk\nk\n");
+    assert_eq!(decisions[0], Decision::Expire);
+}
+
+// ── The command ──────────────────────────────────────────────────────────────
+
+fn revet(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(args)
+        .arg("--print-exit-reason")
+        .current_dir(dir)
+        .env_remove("REVET_LOCALE")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_report_counts_entries_by_status() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "charge.py", SQL_INJECTION);
+    write(dir.path(), "refund.py", SQL_INJECTION);
+    write(dir.path(), "old.py", SQL_INJECTION);
+    assert!(revet(dir.path(), &["baseline"]).status.success());
+
+    fs::remove_file(dir.path().join("old.py")).unwrap();
+    write(dir.path(), "refund.py", "def refund():\n    pass\n");
+
+    let output = revet(
+        dir.path(),
+        &["baseline", "review", "--report", "--format", "json"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["entries"], 3);
+    assert_eq!(report["matched"], 1);
+    assert_eq!(report["unmatched"], 1);
+    assert_eq!(report["file_deleted"], 1);
+    assert_eq!(report["rules"]["security:sql_injection"]["entries"], 3);
+
+    let output = revet(dir.path(), &["baseline", "review", "--report"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("(3 entries)"), "{}", stdout);
+    assert!(stdout
+        .lines()
+        .any(|l| l.split_whitespace().collect::<Vec<_>>() == ["total", "3", "1", "1", "1", "0"]));
+}
+
+#[test]
+fn test_review_needs_a_terminal_and_a_baseline() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "charge.py", SQL_INJECTION);
+
+    let output = revet(dir.path(), &["baseline", "review", "--report"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No baseline to review"));
+
+    // Stdin is not a terminal under test
+    assert!(revet(dir.path(), &["baseline"]).status.success());
+    let output = revet(dir.path(), &["baseline", "review"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("use --report"));
+
+    let output = revet(
+        dir.path(),
+        &["baseline", "review", "--report", "--format", "sarif"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+}
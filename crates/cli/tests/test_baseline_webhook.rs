@@ -0,0 +1,232 @@
+//! Baseline debt deltas end to end: `revet baseline` and full reviews on a
+//! branch, the signed `[reporting]` webhook event (a 503 retried, a dry run
+//! that sends nothing) and `revet trend --baseline-by-owner`
+
+use revet_cli::webhook::{hmac_sha256, sign, EVENT, SIGNATURE_HEADER};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use tiny_http::{Response, Server};
+
+mod common;
+use common::git;
+
+const SECRET: &str = "s3cret";
+
+/// A request the receiver got: headers by lowercase name, and the body
+struct Received {
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Received {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == &name.to_ascii_lowercase())
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap()
+    }
+}
+
+/// A webhook receiver answering its first request 503, then 200
+fn receiver() -> (String, Arc<Mutex<Vec<Received>>>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://{}/hooks/revet",
+        server.server_addr().to_ip().unwrap()
+    );
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&received);
+    std::thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let mut body = Vec::new();
+            let _ = request.as_reader().read_to_end(&mut body);
+            let headers = request
+                .headers()
+                .iter()
+                .map(|h| {
+                    (
+                        h.field.as_str().as_str().to_ascii_lowercase(),
+                        h.value.as_str().to_string(),
+                    )
+                })
+                .collect();
+            let mut log = log.lock().unwrap();
+            let status = if log.is_empty() { 503 } else { 200 };
+            log.push(Received { headers, body });
+            let _ = request.respond(Response::from_string("").with_status_code(status));
+        }
+    });
+    (url, received)
+}
+
+/// A repository on `main` with a debug artifact owned by each of two teams
+fn repo(webhook_url: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    fs::write(
+        root.join(".revet.toml"),
+        format!(
+            "[modules]\ndebug_artifacts = true\n\n[reporting]\nwebhook_url = {:?}\nwebhook_backoff_ms = 10\n",
+            webhook_url
+        ),
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("api")).unwrap();
+    fs::create_dir_all(root.join("web")).unwrap();
+    fs::write(
+        root.join("CODEOWNERS"),
+        "/api/ @acme/api\n/web/ @acme/web\n",
+    )
+    .unwrap();
+    fs::write(root.join("api/app.py"), "def run():\n    breakpoint()\n").unwrap();
+    fs::write(root.join("web/ui.py"), "def show():\n    breakpoint()\n").unwrap();
+    git(root, &["init", "-q", "-b", "main"]);
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-q", "-m", "init"]);
+    dir
+}
+
+fn revet(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(args)
+        .current_dir(dir)
+        .env_remove("REVET_LOCALE")
+        .env_remove("REVET_DATA_DIR")
+        .env_remove("GITHUB_ACTIONS")
+        .env("REVET_WEBHOOK_SECRET", SECRET)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn owner<'a>(event: &'a Value, owner: &str) -> &'a Value {
+    event["owners"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|d| d["owner"] == owner)
+        .unwrap_or_else(|| panic!("no {} in {}", owner, event))
+}
+
+#[test]
+fn test_hmac_sha256_rfc4231() {
+    // RFC 4231, test case 2
+    let mac: String = hmac_sha256(b"Jefe", b"what do ya want for nothing?")
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    assert_eq!(
+        mac,
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+    // Keys longer than a block are hashed first (test case 6)
+    let mac = hmac_sha256(
+        &[0xaa; 131],
+        b"Test Using Larger Than Block-Size Key - Hash Key First",
+    );
+    assert_eq!(mac[..4], [0x60, 0xe4, 0x31, 0x59], "{:02x?}", &mac[..4]);
+}
+
+#[test]
+fn test_baseline_delta_events_and_trend() {
+    let (url, received) = receiver();
+    let dir = repo(&url);
+    let root = dir.path();
+
+    // First baseline: nothing recorded on main yet, an empty delta; the
+    // 503 is retried
+    let output = revet(root, &["baseline"]);
+    assert!(output.status.success(), "{:?}", output);
+    {
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2, "the 503 is retried once");
+        assert_eq!(received[0].body, received[1].body);
+        let request = &received[1];
+        assert_eq!(request.header("Content-Type"), Some("application/json"));
+        assert_eq!(request.header("X-Revet-Event"), Some(EVENT));
+        assert_eq!(
+            request.header(SIGNATURE_HEADER),
+            Some(sign(SECRET, &request.body).as_str())
+        );
+        let event = request.json();
+        assert_eq!(event["event"], EVENT);
+        assert_eq!(event["command"], "baseline");
+        assert_eq!(event["branch"], "main");
+        assert!(event["since"].is_null());
+        assert_eq!(event["total"]["net"], 0);
+    }
+
+    // A full review records the baseline's entries on main
+    revet(root, &["review", "--full"]);
+
+    // The web artifact is fixed, a new api one is baselined
+    fs::write(root.join("web/ui.py"), "def show():\n    pass\n").unwrap();
+    fs::write(root.join("api/jobs.py"), "def job():\n    breakpoint()\n").unwrap();
+    let output = revet(root, &["baseline"]);
+    assert!(output.status.success(), "{:?}", output);
+    let event = received.lock().unwrap().last().unwrap().json();
+    assert!(event["since"].is_string(), "{}", event);
+    assert_eq!(owner(&event, "@acme/api")["added"], 1);
+    assert_eq!(owner(&event, "@acme/web")["removed"], 1);
+    assert_eq!(event["total"]["net"], 0);
+
+    // A dry run prints the signed event and sends nothing
+    let sent = received.lock().unwrap().len();
+    let output = revet(root, &["baseline", "--webhook-dry-run"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("dry run"), "{}", stderr);
+    assert!(stderr.contains("sha256="), "{}", stderr);
+    assert!(
+        stderr.contains("\"event\": \"baseline.delta\""),
+        "{}",
+        stderr
+    );
+    assert_eq!(received.lock().unwrap().len(), sent);
+
+    // The next full review records the changes since the first one
+    revet(root, &["review", "--full"]);
+    let output = revet(root, &["trend", "--baseline-by-owner", "--format", "json"]);
+    assert!(output.status.success(), "{:?}", output);
+    let trend: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(trend["branch"], "main");
+    assert_eq!(trend["runs"], 1);
+    assert_eq!(owner(&trend, "@acme/api")["added"], 1);
+    assert_eq!(owner(&trend, "@acme/web")["removed"], 1);
+    assert_eq!(trend["total"]["net"], 0);
+
+    let output = revet(root, &["trend"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("total"), "{}", stdout);
+    assert!(!stdout.contains("@acme/api"), "{}", stdout);
+}
+
+#[test]
+fn test_unsigned_event_is_not_sent() {
+    let (url, received) = receiver();
+    let dir = repo(&url);
+    let output = Command::new(env!("CARGO_BIN_EXE_revet"))
+        .arg("baseline")
+        .current_dir(dir.path())
+        .env_remove("REVET_WEBHOOK_SECRET")
+        .env_remove("REVET_DATA_DIR")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("$REVET_WEBHOOK_SECRET is not set"),
+        "{}",
+        stderr
+    );
+    assert!(received.lock().unwrap().is_empty());
+}
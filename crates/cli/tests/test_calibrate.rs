@@ -0,0 +1,116 @@
+//! `revet calibrate`: the report and the suggested `[calibration]` section
+//! for a repository of known complexity, and the budget flags
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// Twenty functions of cyclomatic complexity 1 to 20
+fn fixture() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let mut src = String::new();
+    for k in 1..=20 {
+        src.push_str(&format!("def f{}(x):\n", k));
+        for _ in 1..k {
+            src.push_str("    if x > 0: x -= 1\n");
+        }
+        src.push_str("    return x\n\n");
+    }
+    fs::write(dir.path().join("funcs.py"), src).unwrap();
+    dir
+}
+
+fn revet(repo: &Path, args: &[&str]) -> Output {
+    let state = TempDir::new().unwrap();
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .arg("calibrate")
+        .arg(repo)
+        .args(args)
+        .env_remove("REVET_LOCALE")
+        .env_remove("REVET_DATA_DIR")
+        .env("XDG_STATE_HOME", state.path())
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_calibrate_suggests_the_threshold_for_the_budget() {
+    let repo = fixture();
+    let output = revet(repo.path(), &["--max-findings", "5"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{:?}", output);
+
+    assert!(
+        stdout.contains("modules.complexity_threshold — cyclomatic complexity per function"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("count 20, min 1, p50 10, p90 18, p95 19, p99 20, max 20"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("       10        11  current\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("       16         5  suggested\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("[calibration]\nmax_findings = 5\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("# [modules]\n# complexity_threshold = 16\n"),
+        "{}",
+        stdout
+    );
+    // nothing is applied
+    assert!(!repo.path().join(".revet.toml").exists());
+}
+
+#[test]
+fn test_calibrate_reads_the_budget_from_config_and_writes_the_section() {
+    let repo = fixture();
+    fs::write(
+        repo.path().join(".revet.toml"),
+        "[calibration]\npercentile = 90\n",
+    )
+    .unwrap();
+    let before = fs::read_to_string(repo.path().join(".revet.toml")).unwrap();
+    let out = TempDir::new().unwrap();
+    let section = out.path().join("calibration.toml");
+
+    let output = revet(repo.path(), &["--output", section.to_str().unwrap()]);
+    assert!(output.status.success(), "{:?}", output);
+    let written = fs::read_to_string(&section).unwrap();
+    assert!(
+        written.contains("[calibration]\npercentile = 90\n"),
+        "{}",
+        written
+    );
+    assert!(
+        written.contains("# 2 finding(s) at 19 (currently 10: 11 finding(s))"),
+        "{}",
+        written
+    );
+    assert_eq!(
+        fs::read_to_string(repo.path().join(".revet.toml")).unwrap(),
+        before
+    );
+}
+
+#[test]
+fn test_calibrate_rejects_an_out_of_range_percentile() {
+    let repo = fixture();
+    let output = revet(repo.path(), &["--percentile", "100"]);
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--percentile 100 is invalid"));
+}
@@ -0,0 +1,129 @@
+//! `--changed-since <rev>`: only the files changed since `rev` are under
+//! review, with findings anywhere in them, while cross-file analyzers also
+//! see the files one import or call away
+
+use clap::Parser;
+use revet_cli::commands::review;
+use revet_cli::Cli;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+use common::git;
+
+fn cli(args: &[&str]) -> Cli {
+    let mut argv = vec!["revet"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+const QUERY: &str =
+    "def lookup(cursor, uid):\n    cursor.execute(f\"SELECT * FROM t WHERE id = {uid}\")\n";
+
+/// Three files with a SQL injection each, and a commit that edits
+/// `app/api.py` away from its injection and the ignored `vendor/lib.py`
+fn fixture() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("app")).unwrap();
+    fs::create_dir_all(root.join("vendor")).unwrap();
+    fs::write(root.join("app/api.py"), QUERY).unwrap();
+    fs::write(root.join("app/old.py"), QUERY).unwrap();
+    fs::write(root.join("vendor/lib.py"), QUERY).unwrap();
+    fs::write(
+        root.join(".revet.toml"),
+        "[ignore]\npaths = [\"vendor/**\"]\n",
+    )
+    .unwrap();
+    git(root, &["init", "-q", "-b", "main"]);
+    git(root, &["add", "."]);
+    git(root, &["commit", "-q", "-m", "Initial"]);
+    fs::write(
+        root.join("app/api.py"),
+        format!("{}\n\ndef ping():\n    return \"pong\"\n", QUERY),
+    )
+    .unwrap();
+    fs::write(root.join("vendor/lib.py"), format!("{}\n# edited\n", QUERY)).unwrap();
+    git(root, &["commit", "-q", "-am", "Add ping"]);
+    dir
+}
+
+#[test]
+fn test_only_changed_files_reviewed() {
+    let dir = fixture();
+    let root = dir.path();
+    let run = review::analyze(root, &cli(&["--changed-since", "HEAD~1"])).unwrap();
+    assert_eq!(run.summary.files_analyzed, 1);
+    assert!(!run.findings.is_empty());
+    // The injection is on a line the commit did not touch, and still kept
+    assert!(
+        run.findings
+            .iter()
+            .all(|f| f.file == root.join("app/api.py")),
+        "{:?}",
+        run.findings
+    );
+    assert!(run.findings.iter().any(|f| f.line == 2));
+
+    // A full review sees the unchanged file's findings too
+    let full = review::analyze(root, &cli(&["--full"])).unwrap();
+    assert!(full
+        .findings
+        .iter()
+        .any(|f| f.file == root.join("app/old.py")));
+}
+
+#[test]
+fn test_ignored_paths_still_apply() {
+    let dir = fixture();
+    let root = dir.path();
+    let run = review::analyze(
+        root,
+        &cli(&["--changed-since", "HEAD~1", "--coverage-report"]),
+    )
+    .unwrap();
+    let ledger = run.ledger.unwrap();
+    assert!(ledger.skip_reason(&root.join("vendor/lib.py")).is_some());
+    assert_eq!(ledger.skip_reason(&root.join("app/api.py")), None);
+}
+
+#[test]
+fn test_composes_with_module() {
+    let dir = fixture();
+    let root = dir.path();
+    let all = review::analyze(root, &cli(&["--changed-since", "HEAD~1"])).unwrap();
+    let run = review::analyze(
+        root,
+        &cli(&["--changed-since", "HEAD~1", "--module", "security"]),
+    )
+    .unwrap();
+    assert_eq!(run.summary.files_analyzed, 1);
+    assert!(!run.findings.is_empty());
+    assert!(run.findings.len() <= all.findings.len());
+    assert!(run
+        .findings
+        .iter()
+        .all(|f| f.file == root.join("app/api.py")));
+}
+
+#[test]
+fn test_conflicts_with_full_and_diff() {
+    for other in [&["--full"][..], &["--diff", "main"][..]] {
+        let mut argv = vec!["revet", "--changed-since", "HEAD~1"];
+        argv.extend_from_slice(other);
+        assert!(Cli::try_parse_from(argv).is_err(), "{:?}", other);
+    }
+}
+
+#[test]
+fn test_unknown_rev_is_usage_error() {
+    let dir = fixture();
+    let err = review::analyze(dir.path(), &cli(&["--changed-since", "no-such-rev"]))
+        .err()
+        .expect("unknown rev");
+    assert!(
+        format!("{:#}", err).contains("--changed-since"),
+        "{:#}",
+        err
+    );
+}
@@ -0,0 +1,202 @@
+//! Checkstyle XML output: the emitted document parses back to the findings
+//! it was written from
+
+use revet_cli::output::checkstyle::CheckstyleFormatter;
+use revet_cli::output::Reporter;
+use revet_core::{Finding, Severity};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+fn make_finding(id: &str, severity: Severity, message: &str, file: &str, line: usize) -> Finding {
+    Finding {
+        id: id.to_string(),
+        severity,
+        message: message.to_string(),
+        file: PathBuf::from(file),
+        line,
+        ..Default::default()
+    }
+}
+
+fn render(findings: &[Finding]) -> String {
+    let repo = Path::new("/repo");
+    let mut formatter = CheckstyleFormatter::new(repo.to_path_buf());
+    for finding in findings {
+        formatter.report_finding(finding, repo);
+    }
+    formatter.text().unwrap()
+}
+
+/// `(file, line, column, severity, message, source)` of every `<error>`
+type Error = (
+    String,
+    Option<String>,
+    Option<String>,
+    String,
+    String,
+    String,
+);
+
+fn parse(xml: &str) -> Vec<Error> {
+    let doc = roxmltree::Document::parse(xml).unwrap_or_else(|e| panic!("{}\n{}", e, xml));
+    let root = doc.root_element();
+    assert_eq!(root.tag_name().name(), "checkstyle");
+    assert_eq!(root.attribute("version"), Some("4.3"));
+    let mut errors = Vec::new();
+    for file in root.children().filter(|n| n.is_element()) {
+        assert_eq!(file.tag_name().name(), "file");
+        let name = file.attribute("name").unwrap();
+        for error in file.children().filter(|n| n.is_element()) {
+            assert_eq!(error.tag_name().name(), "error");
+            errors.push((
+                name.to_string(),
+                error.attribute("line").map(str::to_string),
+                error.attribute("column").map(str::to_string),
+                error.attribute("severity").unwrap().to_string(),
+                error.attribute("message").unwrap().to_string(),
+                error.attribute("source").unwrap().to_string(),
+            ));
+        }
+    }
+    errors
+}
+
+#[test]
+fn test_round_trip_groups_by_file_and_maps_severities() {
+    let mut with_column = make_finding(
+        "SEC-001",
+        Severity::Error,
+        "Hardcoded AWS key",
+        "/repo/src/config.py",
+        42,
+    );
+    with_column.column = Some(7);
+    let findings = vec![
+        with_column,
+        make_finding(
+            "SQL-002",
+            Severity::Warning,
+            "Query built by concatenation",
+            "/repo/src/db.py",
+            3,
+        ),
+        make_finding(
+            "CMPLX-001",
+            Severity::Info,
+            "Function is long",
+            "/repo/src/config.py",
+            90,
+        ),
+    ];
+
+    let xml = render(&findings);
+    assert!(
+        xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"),
+        "{}",
+        xml
+    );
+    let errors = parse(&xml);
+    let some = |s: &str| Some(s.to_string());
+    assert_eq!(
+        errors,
+        vec![
+            (
+                "src/config.py".to_string(),
+                some("42"),
+                some("7"),
+                "error".to_string(),
+                "Hardcoded AWS key".to_string(),
+                "revet.SEC-001".to_string(),
+            ),
+            (
+                "src/config.py".to_string(),
+                some("90"),
+                None,
+                "info".to_string(),
+                "Function is long".to_string(),
+                "revet.CMPLX-001".to_string(),
+            ),
+            (
+                "src/db.py".to_string(),
+                some("3"),
+                None,
+                "warning".to_string(),
+                "Query built by concatenation".to_string(),
+                "revet.SQL-002".to_string(),
+            ),
+        ]
+    );
+    // one <file> per path
+    assert_eq!(xml.matches("<file ").count(), 2, "{}", xml);
+}
+
+#[test]
+fn test_round_trip_escapes_messages() {
+    let message = "Vec<Box<dyn Fn() -> T>> & \"quoted\" 'text'\nsecond line\ttab";
+    let findings = vec![make_finding(
+        "TYPE-001",
+        Severity::Warning,
+        message,
+        "/repo/src/a&b <c>.rs",
+        1,
+    )];
+
+    let xml = render(&findings);
+    assert!(!xml.contains("Vec<Box"), "{}", xml);
+    let errors = parse(&xml);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, "src/a&b <c>.rs");
+    assert_eq!(errors[0].4, message);
+}
+
+#[test]
+fn test_repository_level_findings_come_first_without_a_line() {
+    let findings = vec![
+        make_finding("SEC-001", Severity::Error, "key", "/repo/app.py", 4),
+        make_finding("REPO-001", Severity::Warning, "No LICENSE file", "", 0),
+    ];
+
+    let errors = parse(&render(&findings));
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].0, "");
+    assert_eq!(errors[0].1, None);
+    assert_eq!(errors[0].5, "revet.REPO-001");
+    assert_eq!(errors[1].0, "app.py");
+
+    assert!(parse(&render(&[])).is_empty());
+}
+
+#[test]
+fn test_review_writes_checkstyle_report() {
+    let repo = TempDir::new().unwrap();
+    fs::write(
+        repo.path().join("charge.py"),
+        "def charge(cursor, user_id):\n    cursor.execute(\"SELECT * FROM users WHERE id = \" + user_id)\n",
+    )
+    .unwrap();
+    let out = TempDir::new().unwrap();
+    let report = out.path().join("revet-checkstyle.xml");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(["--full", "--no-baseline", "--format"])
+        .arg(format!("checkstyle={}", report.display()))
+        .arg("review")
+        .arg(repo.path())
+        .env_remove("REVET_LOCALE")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Report:"), "{}", stderr);
+
+    let errors = parse(&fs::read_to_string(&report).unwrap());
+    assert!(
+        errors
+            .iter()
+            .any(|e| e.0 == "charge.py" && e.5.starts_with("revet.SQL-")),
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,392 @@
+//! Reports over the `[output.sarif]`/`[output.json]` limits, written to a
+//! directory in chunks: every chunk a valid document, every source file in
+//! exactly one chunk, dropped results counted, and chunks uploaded in order
+
+use base64::Engine;
+use flate2::read::GzDecoder;
+use revet_cli::output::chunks::{self, Kind, Limits, ReportIndex, INDEX_FILE};
+use revet_cli::output::code_scanning::{self, UploadContext};
+use revet_cli::output::json::JsonFormatter;
+use revet_cli::output::sarif::build_sarif_log;
+use revet_cli::output::Reporter;
+use revet_core::{Finding, Severity};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use tiny_http::{Response, Server};
+
+mod common;
+use common::sarif_schema_subset;
+
+fn make_finding(id: &str, severity: Severity, file: &str, line: usize) -> Finding {
+    Finding {
+        id: id.to_string(),
+        severity,
+        message: format!("{} at line {}", id, line),
+        file: PathBuf::from(file),
+        line,
+        ..Default::default()
+    }
+}
+
+/// 40 files with 1 to 7 findings each, over three rule prefixes and all
+/// severities
+fn synthetic_findings() -> Vec<Finding> {
+    let prefixes = ["SEC", "SQL", "ML"];
+    let severities = [Severity::Error, Severity::Warning, Severity::Info];
+    let mut findings = Vec::new();
+    for file in 0..40 {
+        for n in 0..(file % 7 + 1) {
+            findings.push(make_finding(
+                &format!("{}-{:03}", prefixes[(file + n) % 3], findings.len() + 1),
+                severities[(file * 3 + n) % 3],
+                &format!("/repo/src/module_{:02}.py", file),
+                n * 10 + 1,
+            ));
+        }
+    }
+    findings
+}
+
+fn sarif_document(findings: &[Finding]) -> Value {
+    serde_json::to_value(build_sarif_log(findings, Path::new("/repo"))).unwrap()
+}
+
+fn read_json(path: &Path) -> Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn read_index(dir: &Path) -> ReportIndex {
+    serde_json::from_str(&fs::read_to_string(dir.join(INDEX_FILE)).unwrap()).unwrap()
+}
+
+fn uri(result: &Value) -> &str {
+    result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"]
+        .as_str()
+        .unwrap()
+}
+
+#[test]
+fn test_sarif_chunks_are_valid_and_partitioned_by_file() {
+    let findings = synthetic_findings();
+    let document = sarif_document(&findings);
+    let all_rules = document["runs"][0]["tool"]["driver"]["rules"].clone();
+    let limits = Limits {
+        max_results: 25,
+        max_bytes: 8_000,
+    };
+    assert!(chunks::overflow(&document, Kind::Sarif, limits).is_some());
+
+    let tmp = TempDir::new().unwrap();
+    let dir = tmp.path().join("out/");
+    let written = chunks::write(document, Kind::Sarif, &dir, limits).unwrap();
+    let index = read_index(&dir);
+    assert_eq!(written.index.as_ref(), Some(&index));
+    assert!(index.chunks.len() > 2, "{:?}", index.chunks);
+    assert_eq!(index.total_results, findings.len());
+    assert_eq!(index.written_results, findings.len());
+    assert_eq!(index.dropped_results, 0);
+
+    let schema = sarif_schema_subset();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut ids = Vec::new();
+    let mut last_path = String::new();
+    for (i, (entry, file)) in index.chunks.iter().zip(&written.files).enumerate() {
+        assert_eq!(file, &dir.join(format!("report-{}.sarif", i + 1)));
+        assert_eq!(entry.file, format!("report-{}.sarif", i + 1));
+        let bytes = fs::metadata(file).unwrap().len();
+        assert_eq!(bytes, entry.bytes);
+        assert!(
+            bytes <= limits.max_bytes,
+            "{} is {} bytes",
+            entry.file,
+            bytes
+        );
+
+        let chunk = read_json(file);
+        if let Err(errors) = schema.validate(&chunk) {
+            let errors: Vec<String> = errors.map(|e| e.to_string()).collect();
+            panic!("{} is not valid SARIF: {:?}", entry.file, errors);
+        }
+        let run = &chunk["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"], all_rules);
+        assert_eq!(
+            run["properties"]["chunk"],
+            json!({ "index": i + 1, "count": index.chunks.len() })
+        );
+        ids.push(run["automationDetails"]["id"].as_str().unwrap().to_string());
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), entry.results);
+        assert!(results.len() <= limits.max_results);
+        assert_eq!(uri(&results[0]), entry.first_path);
+        assert_eq!(uri(results.last().unwrap()), entry.last_path);
+        assert!(entry.first_path > last_path, "chunks out of path order");
+        last_path = entry.last_path.clone();
+        for path in results.iter().map(uri) {
+            let owner = *seen.entry(path.to_string()).or_insert(i);
+            assert_eq!(owner, i, "{} is in chunks {} and {}", path, owner, i);
+        }
+    }
+    assert_eq!(seen.len(), 40);
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), index.chunks.len());
+}
+
+#[test]
+fn test_results_over_the_limit_in_one_file_are_dropped_by_severity() {
+    let mut findings = Vec::new();
+    for line in 1..=12 {
+        let severity = match line % 3 {
+            0 => Severity::Error,
+            1 => Severity::Info,
+            _ => Severity::Warning,
+        };
+        findings.push(make_finding("SEC-001", severity, "/repo/big.py", line));
+    }
+    findings.push(make_finding("SEC-002", Severity::Info, "/repo/small.py", 3));
+    let limits = Limits {
+        max_results: 5,
+        max_bytes: 0,
+    };
+
+    let tmp = TempDir::new().unwrap();
+    let written =
+        chunks::write(sarif_document(&findings), Kind::Sarif, tmp.path(), limits).unwrap();
+    let index = read_index(tmp.path());
+    assert_eq!(index.total_results, 13);
+    assert_eq!(index.written_results, 6);
+    assert_eq!(index.dropped_results, 7);
+    assert_eq!(index.dropped.len(), 1);
+    assert_eq!(index.dropped[0].path, "big.py");
+    assert_eq!(index.dropped[0].count, 7);
+
+    // All four errors, then the first warning; kept in line order
+    let chunk = read_json(&written.files[0]);
+    let kept: Vec<(u64, &str)> = chunk["runs"][0]["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| {
+            (
+                r["locations"][0]["physicalLocation"]["region"]["startLine"]
+                    .as_u64()
+                    .unwrap(),
+                r["level"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        kept,
+        vec![
+            (2, "warning"),
+            (3, "error"),
+            (6, "error"),
+            (9, "error"),
+            (12, "error")
+        ]
+    );
+    let small = read_json(&written.files[1]);
+    assert_eq!(uri(&small["runs"][0]["results"][0]), "small.py");
+}
+
+#[test]
+fn test_single_file_over_the_limit_is_an_error() {
+    let document = sarif_document(&synthetic_findings());
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("report.sarif");
+    let limits = Limits {
+        max_results: 10,
+        max_bytes: 0,
+    };
+    let err = chunks::write(document.clone(), Kind::Sarif, &file, limits).unwrap_err();
+    assert!(err.to_string().contains("pass a directory"), "{}", err);
+    assert!(err.to_string().contains("max_results = 10"), "{}", err);
+    assert!(!file.exists());
+
+    let unlimited = Limits {
+        max_results: 0,
+        max_bytes: 0,
+    };
+    let written = chunks::write(document.clone(), Kind::Sarif, &file, unlimited).unwrap();
+    assert_eq!(written.files, vec![file.clone()]);
+    assert!(written.index.is_none());
+    assert_eq!(read_json(&file), document);
+}
+
+#[test]
+fn test_json_chunks() {
+    let mut formatter = JsonFormatter::new();
+    for finding in synthetic_findings() {
+        formatter.report_finding(&finding, Path::new("/repo"));
+    }
+    let document = formatter.document().unwrap();
+    let limits = Limits {
+        max_results: 30,
+        max_bytes: 0,
+    };
+
+    let tmp = TempDir::new().unwrap();
+    let written = chunks::write(document, Kind::Json, tmp.path(), limits).unwrap();
+    let index = read_index(tmp.path());
+    assert_eq!(index.format, "json");
+    let mut files = Vec::new();
+    for (i, path) in written.files.iter().enumerate() {
+        assert!(path.ends_with(format!("report-{}.json", i + 1)));
+        let chunk = read_json(path);
+        assert_eq!(chunk["chunk"]["index"], i + 1);
+        assert!(chunk.get("summary").is_some());
+        let findings = chunk["findings"].as_array().unwrap();
+        assert!(findings.len() <= 30);
+        let mut paths: Vec<&str> = findings
+            .iter()
+            .map(|f| f["file"].as_str().unwrap())
+            .collect();
+        paths.dedup();
+        files.extend(paths.into_iter().map(str::to_string));
+    }
+    let count = files.len();
+    files.sort();
+    files.dedup();
+    assert_eq!(files.len(), count, "a file is split across chunks");
+    assert_eq!(count, 40);
+}
+
+#[test]
+fn test_stale_chunks_are_removed() {
+    let tmp = TempDir::new().unwrap();
+    for stale in ["report-7.sarif", "report-index.json"] {
+        fs::write(tmp.path().join(stale), "{}").unwrap();
+    }
+    fs::write(tmp.path().join("report-notes.sarif"), "{}").unwrap();
+    let limits = Limits {
+        max_results: 0,
+        max_bytes: 0,
+    };
+    chunks::write(
+        sarif_document(&synthetic_findings()),
+        Kind::Sarif,
+        tmp.path(),
+        limits,
+    )
+    .unwrap();
+    assert!(!tmp.path().join("report-7.sarif").exists());
+    assert!(tmp.path().join("report-notes.sarif").exists());
+    assert!(tmp.path().join("report-1.sarif").exists());
+    assert_eq!(read_index(tmp.path()).chunks.len(), 1);
+}
+
+#[test]
+fn test_chunks_upload_in_order() {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let received: Arc<Mutex<Vec<(String, Value)>>> = Arc::default();
+    let shared = Arc::clone(&received);
+    std::thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let n = {
+                let mut received = shared.lock().unwrap();
+                received.push((
+                    request.url().to_string(),
+                    serde_json::from_str(&body).unwrap(),
+                ));
+                received.len()
+            };
+            let response =
+                Response::from_string(format!("{{\"id\":\"upload-{}\"}}", n)).with_status_code(202);
+            let _ = request.respond(response);
+        }
+    });
+
+    let tmp = TempDir::new().unwrap();
+    let limits = Limits {
+        max_results: 40,
+        max_bytes: 0,
+    };
+    let written = chunks::write(
+        sarif_document(&synthetic_findings()),
+        Kind::Sarif,
+        tmp.path(),
+        limits,
+    )
+    .unwrap();
+    let ctx = UploadContext {
+        api_url: url,
+        token: "t0ken".to_string(),
+        repository: "octo/app".to_string(),
+        commit_sha: "abc123".to_string(),
+        git_ref: "refs/heads/main".to_string(),
+    };
+    let ids = code_scanning::upload(&written.files, &ctx).unwrap();
+    assert_eq!(ids.len(), written.files.len());
+    assert_eq!(ids[0], "upload-1");
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), written.files.len());
+    for ((url, body), file) in received.iter().zip(&written.files) {
+        assert_eq!(url, "/repos/octo/app/code-scanning/sarifs");
+        assert_eq!(body["commit_sha"], "abc123");
+        assert_eq!(body["ref"], "refs/heads/main");
+        let gz = base64::engine::general_purpose::STANDARD
+            .decode(body["sarif"].as_str().unwrap())
+            .unwrap();
+        let mut sarif = String::new();
+        GzDecoder::new(&gz[..]).read_to_string(&mut sarif).unwrap();
+        assert_eq!(sarif, fs::read_to_string(file).unwrap());
+    }
+}
+
+const SQL_INJECTION: &str =
+    "def charge(cursor, user_id):\n    cursor.execute(\"SELECT * FROM users WHERE id = \" + user_id)\n";
+
+fn revet(dir: &Path, output: &Path) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(["--full", "--format", "sarif", "--no-baseline", "--output"])
+        .arg(output)
+        .arg("review")
+        .arg(dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_output_flag() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join(".revet.toml"),
+        "[output.sarif]\nmax_results = 1\n",
+    )
+    .unwrap();
+    for name in ["charge", "refund"] {
+        fs::write(
+            dir.path().join(format!("{}.py", name)),
+            SQL_INJECTION.replace("charge", name),
+        )
+        .unwrap();
+    }
+
+    let out = TempDir::new().unwrap();
+    let result = revet(dir.path(), &out.path().join("report.sarif"));
+    assert_eq!(result.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&result.stderr).contains("pass a directory to --output"));
+
+    let result = revet(dir.path(), out.path());
+    assert!(result.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(
+        stderr.contains("2 SARIF files with 2 results"),
+        "{}",
+        stderr
+    );
+    let index = read_index(out.path());
+    let paths: Vec<&str> = index.chunks.iter().map(|c| c.first_path.as_str()).collect();
+    assert_eq!(paths, vec!["charge.py", "refund.py"]);
+}
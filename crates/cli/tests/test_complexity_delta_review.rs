@@ -0,0 +1,79 @@
+use clap::Parser;
+use revet_cli::commands::review;
+use revet_cli::Cli;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod common;
+use common::git;
+
+fn cli(args: &[&str]) -> Cli {
+    let mut argv = vec!["revet"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+/// The `complexity_delta` fixture as a repository: `base/` committed,
+/// `head/` in the working tree, with `mode` in `[analyzers.complexity]`
+fn fixture_repo(mode: &str) -> TempDir {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/complexity_delta");
+    let dir = TempDir::new().unwrap();
+    let config = fs::read_to_string(src.join(".revet.toml"))
+        .unwrap()
+        .replace("mode = \"delta\"", &format!("mode = \"{}\"", mode));
+    fs::write(dir.path().join(".revet.toml"), config).unwrap();
+    fs::copy(src.join("base/billing.py"), dir.path().join("billing.py")).unwrap();
+    git(dir.path(), &["init", "-q"]);
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-q", "-m", "base"]);
+    fs::copy(src.join("head/billing.py"), dir.path().join("billing.py")).unwrap();
+    dir
+}
+
+fn complexity_messages(run: &review::ReviewRun) -> Vec<&str> {
+    let mut messages: Vec<&str> = run
+        .findings
+        .iter()
+        .filter(|f| f.id.starts_with("CMPLX-"))
+        .map(|f| f.message.as_str())
+        .collect();
+    messages.sort();
+    messages
+}
+
+#[test]
+fn test_diff_review_reports_only_worse_and_new_functions() {
+    let dir = fixture_repo("delta");
+    let run = review::analyze(dir.path(), &cli(&["--diff", "HEAD"])).unwrap();
+    assert_eq!(
+        complexity_messages(&run),
+        vec![
+            "Function `loyalty_points` has cyclomatic complexity of 6 (recommended: <5)",
+            "Function `shipping_cost` got more complex in this change: cyclomatic complexity 6 → 7 (threshold 5)",
+        ]
+    );
+    let delta = run.summary.complexity_delta.unwrap();
+    assert_eq!((delta.reported, delta.deferred, delta.absolute), (2, 2, 4));
+    assert_eq!(run.summary.warnings, 2);
+}
+
+#[test]
+fn test_full_review_keeps_absolute_thresholds() {
+    let dir = fixture_repo("delta");
+    let run = review::analyze(dir.path(), &cli(&["--full"])).unwrap();
+    assert_eq!(complexity_messages(&run).len(), 4);
+    assert_eq!(run.summary.complexity_delta, None);
+}
+
+#[test]
+fn test_flag_enables_delta_mode() {
+    let dir = fixture_repo("absolute");
+    let run = review::analyze(dir.path(), &cli(&["--diff", "HEAD"])).unwrap();
+    assert_eq!(complexity_messages(&run).len(), 4);
+    assert_eq!(run.summary.complexity_delta, None);
+
+    let run = review::analyze(dir.path(), &cli(&["--diff", "HEAD", "--complexity-delta"])).unwrap();
+    assert_eq!(complexity_messages(&run).len(), 2);
+    assert!(run.summary.complexity_delta.is_some());
+}
@@ -0,0 +1,154 @@
+//! `CONF` findings for changes to `.revet.toml` in `revet diff` and
+//! `revet --diff`, on the `config_drift` fixture: `base/` committed, `head/`
+//! in the working tree
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+mod common;
+use common::git;
+
+fn fixture_repo() -> TempDir {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/config_drift");
+    let dir = TempDir::new().unwrap();
+    for file in [".revet.toml", "app.py"] {
+        fs::copy(src.join("base").join(file), dir.path().join(file)).unwrap();
+    }
+    git(dir.path(), &["init", "-q"]);
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-q", "-m", "base"]);
+    fs::copy(src.join("head/.revet.toml"), dir.path().join(".revet.toml")).unwrap();
+    dir
+}
+
+fn revet(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(args)
+        .current_dir(dir)
+        .env_remove("REVET_LOCALE")
+        .env_remove("GITHUB_ACTIONS")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+/// `(message, severity, file name, line)` of the `CONF` findings of `revet diff HEAD`
+fn drift(dir: &Path) -> Vec<(String, String, String, u64)> {
+    let output = revet(dir, &["diff", "HEAD", "--format", "json"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap_or_else(|_| {
+        panic!("{}", String::from_utf8_lossy(&output.stderr));
+    });
+    let mut rows: Vec<_> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|f| f["id"].as_str().unwrap().starts_with("CONF-"))
+        .map(|f| {
+            (
+                f["message"].as_str().unwrap().to_string(),
+                f["severity"].as_str().unwrap().to_string(),
+                f["file"]
+                    .as_str()
+                    .unwrap()
+                    .rsplit('/')
+                    .next()
+                    .unwrap()
+                    .to_string(),
+                f["line"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    rows.sort_by_key(|row| row.3);
+    rows
+}
+
+fn row(message: &str, severity: &str, file: &str, line: u64) -> (String, String, String, u64) {
+    (message.into(), severity.into(), file.into(), line)
+}
+
+#[test]
+fn test_disabled_analyzer_and_raised_threshold_are_warnings_at_their_lines() {
+    let dir = fixture_repo();
+    assert_eq!(
+        drift(dir.path()),
+        vec![
+            row(
+                "This change disables the `security` analyzer",
+                "warning",
+                ".revet.toml",
+                5,
+            ),
+            row(
+                "`modules.complexity_threshold` raised from 10 to 15 \u{2014} fewer findings are reported",
+                "warning",
+                ".revet.toml",
+                7,
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_strengthening_is_info_and_unchanged_config_reports_nothing() {
+    let dir = fixture_repo();
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/config_drift");
+    fs::copy(src.join("base/.revet.toml"), dir.path().join(".revet.toml")).unwrap();
+    assert!(drift(dir.path()).is_empty());
+
+    let config = fs::read_to_string(dir.path().join(".revet.toml"))
+        .unwrap()
+        .replace("fail_on = \"error\"", "fail_on = \"warning\"");
+    fs::write(dir.path().join(".revet.toml"), config).unwrap();
+    assert_eq!(
+        drift(dir.path()),
+        vec![row(
+            "`fail_on` tightened from error to warning",
+            "info",
+            ".revet.toml",
+            2
+        )]
+    );
+}
+
+#[test]
+fn test_policy_escalates_weakening_changes() {
+    let dir = fixture_repo();
+    let config = dir.path().join(".revet.toml");
+    let mut text = fs::read_to_string(&config).unwrap();
+    text.push_str(concat!(
+        "\n[[policy]]\n",
+        "name = \"config-sign-off\"\n",
+        "filter = 'prefix == CONF and severity == warning'\n",
+        "aggregate = \"exists\"\n",
+    ));
+    fs::write(&config, text).unwrap();
+
+    let output = revet(dir.path(), &["--diff", "HEAD", "--fail-on", "never"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_markdown_lists_config_changes_in_their_own_section() {
+    let dir = fixture_repo();
+    let output = revet(
+        dir.path(),
+        &["diff", "HEAD", "--format", "markdown", "--fail-on", "never"],
+    );
+    let report = String::from_utf8_lossy(&output.stdout);
+    let section = report
+        .split("### Configuration changes")
+        .nth(1)
+        .unwrap_or_else(|| panic!("{}", report));
+    assert!(
+        section.contains("This change disables the `security` analyzer"),
+        "{}",
+        report
+    );
+    assert!(
+        section.contains("| `.revet.toml` | 7 | Warning |"),
+        "{}",
+        report
+    );
+}
@@ -0,0 +1,237 @@
+//! `revet daemon`: runs served over the socket match in-process runs, the
+//! resident state follows config edits, and the socket is the user's alone
+#![cfg(unix)]
+
+use revet_cli::commands::daemon::{read_frame, write_frame, Reply, Request, RequestKind};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+fn copy_tree(from: &Path, to: &Path) {
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            fs::create_dir_all(&target).unwrap();
+            copy_tree(&entry.path(), &target);
+        } else {
+            fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+/// The `api_dashboard` fixture as a git repository with its own state
+/// home, stopping its daemon when dropped
+struct Repo {
+    dir: TempDir,
+    state: TempDir,
+}
+
+impl Repo {
+    fn new() -> Self {
+        let dir = TempDir::new().unwrap();
+        copy_tree(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/api_dashboard"),
+            dir.path(),
+        );
+        assert!(Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(dir.path())
+            .status()
+            .unwrap()
+            .success());
+        let repo = Self {
+            dir,
+            state: TempDir::new().unwrap(),
+        };
+        // The first run caches the graph later runs measure impact against
+        repo.command(&["review", "--full"])
+            .env("REVET_NO_DAEMON", "1")
+            .output()
+            .unwrap();
+        repo
+    }
+
+    fn path(&self) -> PathBuf {
+        fs::canonicalize(self.dir.path()).unwrap()
+    }
+
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_revet"));
+        command
+            .args(args)
+            .current_dir(self.path())
+            .env_remove("REVET_DATA_DIR")
+            .env_remove("REVET_LOCALE")
+            .env_remove("REVET_NO_DAEMON")
+            .env("XDG_STATE_HOME", self.state.path())
+            .env("NO_COLOR", "1")
+            .env("CI", "true");
+        command
+    }
+
+    fn run(&self, args: &[&str]) -> Output {
+        let output = self.command(args).output().unwrap();
+        assert!(
+            output.status.success(),
+            "{:?}: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output
+    }
+
+    /// `review --full --format json` through the daemon and in process
+    fn reviews(&self) -> (Output, Output) {
+        let args = ["review", "--full", "--format", "json"];
+        let served = self.command(&args).output().unwrap();
+        let local = self
+            .command(&args)
+            .env("REVET_NO_DAEMON", "1")
+            .output()
+            .unwrap();
+        (served, local)
+    }
+
+    fn status(&self) -> String {
+        String::from_utf8_lossy(&self.run(&["daemon", "status"]).stdout).into_owned()
+    }
+
+    fn socket(&self) -> PathBuf {
+        let mut sockets = Vec::new();
+        let mut dirs = vec![self.state.path().to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            for entry in fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if path.ends_with("daemon.sock") {
+                    sockets.push(path);
+                }
+            }
+        }
+        assert_eq!(sockets.len(), 1, "{:?}", sockets);
+        sockets.remove(0)
+    }
+}
+
+impl Drop for Repo {
+    fn drop(&mut self) {
+        let _ = self.command(&["daemon", "stop"]).output();
+    }
+}
+
+fn assert_same(served: &Output, local: &Output) {
+    assert_eq!(served.status.code(), local.status.code());
+    assert_eq!(
+        String::from_utf8_lossy(&served.stdout),
+        String::from_utf8_lossy(&local.stdout)
+    );
+}
+
+fn wait_for(mut done: impl FnMut() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !done() {
+        assert!(Instant::now() < deadline, "timed out");
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+#[test]
+fn test_served_runs_match_in_process_runs() {
+    let repo = Repo::new();
+    repo.run(&["daemon", "start", "--idle-timeout", "60s"]);
+    assert!(repo.status().contains("0 run(s)"), "{}", repo.status());
+
+    let (served, local) = repo.reviews();
+    assert_same(&served, &local);
+    assert_ne!(served.status.code(), Some(0), "fixture has findings");
+    let (served, local) = repo.reviews();
+    assert_same(&served, &local);
+    assert!(repo.status().contains("2 run(s)"), "{}", repo.status());
+    assert!(
+        !repo.status().contains(" 0 file(s) resident"),
+        "{}",
+        repo.status()
+    );
+
+    // Another environment gets a worker of its own, with that environment
+    let args = ["review", "--full", "--format", "json"];
+    let with_env = |daemon: bool| {
+        let mut command = repo.command(&args);
+        command.env("REVET_TEST_CLIENT", "other");
+        if !daemon {
+            command.env("REVET_NO_DAEMON", "1");
+        }
+        command.output().unwrap()
+    };
+    assert_same(&with_env(true), &with_env(false));
+    assert!(repo.status().contains("3 run(s)"), "{}", repo.status());
+
+    // Editing the config drops the resident state, and runs follow it
+    fs::write(
+        repo.path().join(".revet.toml"),
+        "[general]\nfail_on = \"never\"\n",
+    )
+    .unwrap();
+    wait_for(|| repo.status().contains("dropped"));
+    let (served, local) = repo.reviews();
+    assert_eq!(served.status.code(), Some(0));
+    assert_same(&served, &local);
+}
+
+#[test]
+fn test_socket_is_private_and_requests_outside_the_repo_are_rejected() {
+    let repo = Repo::new();
+    repo.run(&["daemon", "start", "--idle-timeout", "60s"]);
+    let socket = repo.socket();
+    let mode = fs::metadata(&socket).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    let dir_mode = fs::metadata(socket.parent().unwrap())
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(dir_mode & 0o777, 0o700);
+
+    let elsewhere = TempDir::new().unwrap();
+    let request = |cwd: &Path, version: &str| {
+        let mut stream = UnixStream::connect(&socket).unwrap();
+        let mut request = Request::new(RequestKind::Run {
+            cwd: cwd.to_path_buf(),
+            args: vec!["revet".into(), "review".into()],
+            env: Vec::new(),
+            color: false,
+        });
+        request.version = version.to_string();
+        write_frame(&mut stream, &request).unwrap();
+        read_frame::<Reply>(&mut stream).unwrap().unwrap()
+    };
+    match request(elsewhere.path(), revet_core::VERSION) {
+        Reply::Rejected { reason } => assert!(reason.contains("outside"), "{}", reason),
+        other => panic!("{:?}", other),
+    }
+    match request(&repo.path(), "0.0.0") {
+        Reply::Rejected { reason } => assert!(reason.contains("0.0.0"), "{}", reason),
+        other => panic!("{:?}", other),
+    }
+
+    repo.run(&["daemon", "stop"]);
+    wait_for(|| !socket.exists());
+    assert!(repo.status().contains("No daemon running"));
+}
+
+#[test]
+fn test_idle_daemon_exits() {
+    let repo = Repo::new();
+    repo.run(&["daemon", "start", "--idle-timeout", "1s"]);
+    let socket = repo.socket();
+    wait_for(|| !socket.exists());
+
+    // Without a daemon, runs are in process
+    let (served, local) = repo.reviews();
+    assert_same(&served, &local);
+}
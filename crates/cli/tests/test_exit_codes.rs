@@ -0,0 +1,216 @@
+//! The exit-code contract: one exit code per failure class, and the
+//! `--print-exit-reason` line
+
+use revet_cli::exit::{Classify, ErrorKind, Outcome, RevetError};
+use revet_core::Cancelled;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+mod common;
+use common::git;
+
+const SQL_INJECTION: &str =
+    "def charge(cursor, user_id):\n    cursor.execute(\"SELECT * FROM users WHERE id = \" + user_id)\n";
+
+/// A committed git repository holding `file` with `content`
+fn repo(file: &str, content: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join(file), content).unwrap();
+    git(dir.path(), &["init", "-q"]);
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-q", "-m", "base"]);
+    dir
+}
+
+fn revet(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(args)
+        .arg("--print-exit-reason")
+        .current_dir(dir)
+        .env_remove("REVET_LOCALE")
+        .env_remove("GITHUB_ACTIONS")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+/// The `--print-exit-reason` line: the last line on stderr
+fn reason(output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    stderr.lines().last().unwrap_or_default().to_string()
+}
+
+#[test]
+fn test_clean_review_exits_0() {
+    let dir = repo("app.py", "def add(a, b):\n    return a + b\n");
+    let output = revet(dir.path(), &["--full"]);
+    assert_eq!(output.status.code(), Some(0), "{}", reason(&output));
+    assert_eq!(reason(&output), "exit=0 reason=success");
+}
+
+#[test]
+fn test_findings_over_the_threshold_exit_1() {
+    let dir = repo("charge.py", SQL_INJECTION);
+    let output = revet(dir.path(), &["--full", "--fail-on", "error"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        reason(&output),
+        "exit=1 reason=findings_exceed_threshold count=1 threshold=error"
+    );
+
+    let output = revet(dir.path(), &["--full", "--gate", "error:0"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        reason(&output),
+        "exit=1 reason=findings_exceed_threshold count=1 threshold=gate"
+    );
+
+    let output = revet(dir.path(), &["--full", "--fail-on", "never"]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_fail_on_uses_severity_overrides() {
+    let dir = repo("charge.py", SQL_INJECTION);
+
+    // Downgraded: no error left
+    fs::write(
+        dir.path().join(".revet.toml"),
+        "[severity]
+SQL = \"info\"\n",
+    )
+    .unwrap();
+    let output = revet(dir.path(), &["--full", "--fail-on", "error"]);
+    assert_eq!(output.status.code(), Some(0), "{}", reason(&output));
+    let output = revet(dir.path(), &["--full", "--fail-on", "info"]);
+    assert_eq!(output.status.code(), Some(1), "{}", reason(&output));
+
+    // Turned off: nothing left at all
+    fs::write(
+        dir.path().join(".revet.toml"),
+        "[severity]
+\"SQL-001\" = \"off\"\n",
+    )
+    .unwrap();
+    let output = revet(dir.path(), &["--full", "--fail-on", "info"]);
+    assert_eq!(output.status.code(), Some(0), "{}", reason(&output));
+
+    // A misspelt severity fails the load and names the key
+    fs::write(
+        dir.path().join(".revet.toml"),
+        "[severity]
+SQL = \"eror\"\n",
+    )
+    .unwrap();
+    let output = revet(dir.path(), &["--full"]);
+    assert_eq!(output.status.code(), Some(2), "{}", reason(&output));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("[severity] \"SQL\" = \"eror\" is invalid"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_failed_policy_exits_1() {
+    let dir = repo("charge.py", SQL_INJECTION);
+    fs::write(
+        dir.path().join(".revet.toml"),
+        r#"
+[[policy]]
+name = "no-errors"
+filter = "severity == error"
+aggregate = "exists"
+"#,
+    )
+    .unwrap();
+    let output = revet(dir.path(), &["--full", "--fail-on", "never"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        reason(&output),
+        "exit=1 reason=policy_failed policies=no-errors"
+    );
+}
+
+#[test]
+fn test_usage_and_config_errors_exit_2() {
+    let dir = repo("app.py", "x = 1\n");
+
+    // Flags clap rejects
+    let output = revet(dir.path(), &["--no-such-flag"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    for args in [
+        &["--full", "--module", "no-such-module"][..],
+        &["--full", "--profile", "no-such-profile"],
+        &["--full", "--estimate-only"],
+    ] {
+        let output = revet(dir.path(), args);
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert_eq!(reason(&output), "exit=2 reason=usage_error", "{:?}", args);
+    }
+
+    // Invalid `.revet.toml`, for a review and for `config check`
+    fs::write(dir.path().join(".revet.toml"), "[general\n").unwrap();
+    for args in [&["--full"][..], &["diff", "HEAD"], &["validate-config"]] {
+        let output = revet(dir.path(), args);
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert_eq!(reason(&output), "exit=2 reason=usage_error", "{:?}", args);
+    }
+}
+
+#[test]
+fn test_environment_errors_exit_3() {
+    // Not a git repository
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("app.py"), "x = 1\n").unwrap();
+    let output = revet(dir.path(), &["diff", "main"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(reason(&output), "exit=3 reason=environment_error");
+
+    // Missing base ref
+    let dir = repo("app.py", "x = 1\n");
+    let output = revet(dir.path(), &["diff", "no-such-branch"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(reason(&output), "exit=3 reason=environment_error");
+}
+
+#[test]
+fn test_errors_are_classified_through_context() {
+    let usage: anyhow::Result<()> = Err(anyhow::anyhow!("bad value")).usage_error();
+    let err = anyhow::Context::context(usage, "while resolving flags").unwrap_err();
+    assert_eq!(ErrorKind::of(&err), ErrorKind::Usage);
+    assert_eq!(format!("{:#}", err), "while resolving flags: bad value");
+
+    let env = RevetError::new(ErrorKind::Environment, anyhow::anyhow!("no repo"));
+    assert_eq!(ErrorKind::of(&env.into()), ErrorKind::Environment);
+
+    // Unclassified errors are internal; a cancelled run was interrupted
+    let err = anyhow::anyhow!("analyzer crashed");
+    assert_eq!(ErrorKind::of(&err), ErrorKind::Internal);
+    assert_eq!(ErrorKind::of(&err).code(), 4);
+    assert_eq!(ErrorKind::of(&Cancelled.into()), ErrorKind::Interrupted);
+}
+
+#[test]
+fn test_outcome_codes_and_reasons() {
+    assert_eq!(Outcome::Success.code(), 0);
+    assert_eq!(
+        Outcome::NotReproduced.reason(),
+        "exit=1 reason=not_reproduced"
+    );
+    assert_eq!(
+        Outcome::ReposFailed { count: 2 }.reason(),
+        "exit=1 reason=repos_failed count=2"
+    );
+    assert_eq!(
+        Outcome::IssuesFailed { failures: 1 }.reason(),
+        "exit=1 reason=issues_failed failures=1"
+    );
+    assert_eq!(Outcome::Interrupted.code(), 130);
+    assert_eq!(Outcome::Interrupted.reason(), "exit=130 reason=interrupted");
+    assert_eq!(ErrorKind::Internal.reason(), "exit=4 reason=internal_error");
+}
@@ -0,0 +1,102 @@
+//! A finding's details in each output: the terminal only under `--verbose`,
+//! JSON and SARIF in full, markdown collapsed, GitHub annotations pointing to
+//! `revet explain`, which prints them for the last run's finding
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+const SQL_INJECTION: &str =
+    "def charge(cursor, user_id):\n    cursor.execute(\"SELECT * FROM users WHERE id = \" + user_id)\n";
+const QUERY: &str = "cursor.execute(\"SELECT * FROM users WHERE id = \" + user_id)";
+
+fn repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("charge.py"), SQL_INJECTION).unwrap();
+    dir
+}
+
+fn revet(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(args)
+        .current_dir(dir)
+        .env_remove("REVET_LOCALE")
+        .env_remove("REVET_DATA_DIR")
+        .env_remove("GITHUB_ACTIONS")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn review(dir: &Path, args: &[&str]) -> String {
+    let mut all = vec!["--full", "--no-baseline", "--fail-on", "never"];
+    all.extend(args);
+    String::from_utf8_lossy(&revet(dir, &all).stdout).into_owned()
+}
+
+#[test]
+fn test_terminal_shows_details_only_when_verbose() {
+    let dir = repo();
+    let plain = review(dir.path(), &[]);
+    assert!(plain.contains("Possible SQL injection"), "{}", plain);
+    assert!(!plain.contains("Query:"), "{}", plain);
+
+    let verbose = review(dir.path(), &["--verbose"]);
+    assert!(verbose.contains("Query:"), "{}", verbose);
+    assert!(verbose.contains(QUERY), "{}", verbose);
+}
+
+#[test]
+fn test_json_and_sarif_carry_details() {
+    let dir = repo();
+    let json: serde_json::Value =
+        serde_json::from_str(&review(dir.path(), &["--format", "json"])).unwrap();
+    assert_eq!(json["findings"][0]["details"]["query_text"], QUERY);
+
+    let sarif: serde_json::Value =
+        serde_json::from_str(&review(dir.path(), &["--format", "sarif"])).unwrap();
+    assert_eq!(
+        sarif["runs"][0]["results"][0]["properties"]["details"]["query_text"],
+        QUERY
+    );
+}
+
+#[test]
+fn test_github_and_markdown_render_details() {
+    let dir = repo();
+    let github = review(dir.path(), &["--format", "github"]);
+    let annotation = github
+        .lines()
+        .find(|l| l.starts_with("::error") || l.starts_with("::warning"))
+        .expect(&github);
+    assert!(
+        annotation.ends_with("(details: run `revet explain SQL-001`)"),
+        "{}",
+        annotation
+    );
+    assert!(!annotation.contains("SELECT"), "{}", annotation);
+
+    let markdown = review(dir.path(), &["--format", "markdown"]);
+    assert!(
+        markdown.contains("<details><summary>Details</summary>Query:<br>"),
+        "{}",
+        markdown
+    );
+}
+
+#[test]
+fn test_explain_prints_the_last_runs_details() {
+    let dir = repo();
+    review(dir.path(), &[]);
+
+    let output = revet(dir.path(), &["explain", "SQL-001"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("SQL Injection"), "{}", stdout);
+    assert!(stdout.contains("This Finding: charge.py:2"), "{}", stdout);
+    assert!(stdout.contains(QUERY), "{}", stdout);
+
+    // A bare prefix explains the category alone
+    let output = revet(dir.path(), &["explain", "SQL"]);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("This Finding"));
+}
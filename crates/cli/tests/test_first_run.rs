@@ -0,0 +1,164 @@
+//! Zero-config first runs: without any `.revet.toml` in the layering chain,
+//! `revet` reviews with the curated `first-run` profile, shows a few findings
+//! per rule and ends with next steps; any config switches back to the
+//! normal defaults
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn copy_dir(src: &Path, dst: &Path) {
+    fs::create_dir_all(dst).unwrap();
+    for entry in fs::read_dir(src).unwrap() {
+        let path = entry.unwrap().path();
+        let target = dst.join(path.file_name().unwrap());
+        if path.is_dir() {
+            copy_dir(&path, &target);
+        } else {
+            fs::copy(&path, &target).unwrap();
+        }
+    }
+}
+
+/// A copy of the polyglot `first_run` fixture at `dir`
+fn fixture(dir: &Path) {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/first_run");
+    copy_dir(&src, dir);
+}
+
+fn revet(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(args)
+        .current_dir(dir)
+        .env_remove("REVET_LOCALE")
+        .env_remove("REVET_DATA_DIR")
+        .env_remove("GITHUB_ACTIONS")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+/// JSON report of a review, and its stderr
+fn review(dir: &Path, args: &[&str]) -> (serde_json::Value, String) {
+    let mut all = vec!["--format", "json", "--fail-on", "never"];
+    all.extend(args);
+    let output = revet(dir, &all);
+    let json = serde_json::from_slice(&output.stdout).unwrap();
+    (json, String::from_utf8_lossy(&output.stderr).into_owned())
+}
+
+/// Findings shown, by ID prefix
+fn shown(json: &serde_json::Value) -> BTreeMap<String, usize> {
+    let mut prefixes = BTreeMap::new();
+    for finding in json["findings"].as_array().unwrap() {
+        let id = finding["id"].as_str().unwrap();
+        *prefixes
+            .entry(id.rsplit_once('-').unwrap().0.to_string())
+            .or_default() += 1;
+    }
+    prefixes
+}
+
+#[test]
+fn test_first_run_runs_the_curated_analyzers() {
+    let dir = TempDir::new().unwrap();
+    fixture(dir.path());
+    let (json, _) = review(dir.path(), &[]);
+
+    // Security at full strength, complexity and unused exports at
+    // conservative thresholds; cycles and everything else off
+    assert_eq!(
+        shown(&json).keys().collect::<Vec<_>>(),
+        ["CMPLX", "DEAD", "SQL"]
+    );
+    let complex: Vec<&str> = json["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|f| f["id"].as_str().unwrap().starts_with("CMPLX"))
+        .map(|f| f["message"].as_str().unwrap())
+        .collect();
+    assert_eq!(complex.len(), 1, "{:?}", complex);
+    assert!(complex[0].contains("`price`"), "{}", complex[0]);
+}
+
+#[test]
+fn test_first_run_caps_findings_per_rule() {
+    let dir = TempDir::new().unwrap();
+    fixture(dir.path());
+    let (json, stderr) = review(dir.path(), &[]);
+
+    // Seven injections and ten unused exports, five of each shown; the
+    // summary counts them all
+    assert_eq!(shown(&json)["SQL"], 5);
+    assert_eq!(shown(&json)["DEAD"], 5);
+    assert_eq!(json["summary"]["errors"], 7);
+    assert_eq!(json["summary"]["warnings"], 11);
+
+    assert!(stderr.contains("Next steps"), "{}", stderr);
+    assert!(
+        stderr.contains("7 findings were hidden by first-run defaults (7 beyond 5 per rule)"),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("revet init"), "{}", stderr);
+    assert!(stderr.contains("revet baseline"), "{}", stderr);
+    for module in ["react", "async_patterns", "error_handling", "infra"] {
+        assert!(stderr.contains(module), "{} missing:\n{}", module, stderr);
+    }
+}
+
+#[test]
+fn test_empty_config_switches_to_normal_defaults() {
+    let dir = TempDir::new().unwrap();
+    fixture(dir.path());
+    fs::write(dir.path().join(".revet.toml"), "").unwrap();
+    let (json, stderr) = review(dir.path(), &[]);
+
+    assert_eq!(
+        shown(&json),
+        BTreeMap::from([("CYCLE".to_string(), 1), ("SQL".to_string(), 7)])
+    );
+    assert!(!stderr.contains("Next steps"), "{}", stderr);
+}
+
+#[test]
+fn test_config_in_a_parent_directory_counts() {
+    let dir = TempDir::new().unwrap();
+    fixture(&dir.path().join("service"));
+    fs::write(dir.path().join(".revet.toml"), "").unwrap();
+    let (json, stderr) = review(&dir.path().join("service"), &[]);
+
+    assert_eq!(shown(&json)["SQL"], 7);
+    assert!(!stderr.contains("Next steps"), "{}", stderr);
+}
+
+#[test]
+fn test_explicit_flags_skip_first_run() {
+    let dir = TempDir::new().unwrap();
+    fixture(dir.path());
+
+    let (json, stderr) = review(dir.path(), &["--full"]);
+    assert_eq!(shown(&json)["SQL"], 7);
+    assert!(!stderr.contains("Next steps"), "{}", stderr);
+
+    let (json, _) = review(dir.path(), &["--profile", "standard"]);
+    assert_eq!(shown(&json)["SQL"], 7);
+}
+
+#[test]
+fn test_init_enables_the_suggested_modules() {
+    let dir = TempDir::new().unwrap();
+    fixture(dir.path());
+    let output = revet(dir.path(), &["init"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("react"), "{}", stdout);
+
+    let config = fs::read_to_string(dir.path().join(".revet.toml")).unwrap();
+    for module in ["react", "async_patterns", "error_handling", "infra"] {
+        let line = format!("\n{} = true\n", module);
+        assert!(config.contains(&line), "{} missing:\n{}", module, config);
+    }
+}
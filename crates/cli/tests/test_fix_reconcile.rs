@@ -0,0 +1,127 @@
+//! `--fix` moves everything that holds a line with the edits: the reported
+//! findings, the last-run log and, under `[baseline] auto_reconcile`, the
+//! baseline's anchors, dropping the entries whose line a fix deleted
+
+use revet_core::Baseline;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+const APP: &str = "\
+def charge(cursor, user_id):
+    breakpoint()
+    cursor.execute(\"SELECT * FROM users WHERE id = \" + user_id)
+
+
+def refund(cursor, order_id):
+    cursor.execute(\"DELETE FROM orders WHERE id = \" + order_id)
+";
+
+fn repo(auto_reconcile: bool) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join(".revet.toml"),
+        format!(
+            "[modules]\ndebug_artifacts = true\n\n[baseline]\nauto_reconcile = {}\n",
+            auto_reconcile
+        ),
+    )
+    .unwrap();
+    fs::write(dir.path().join("app.py"), APP).unwrap();
+    dir
+}
+
+fn revet(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(args)
+        .current_dir(dir)
+        .env_remove("REVET_LOCALE")
+        .env_remove("REVET_DATA_DIR")
+        .env_remove("GITHUB_ACTIONS")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+/// `(ID prefix, line)` of the findings of a JSON report
+fn lines(json: &serde_json::Value) -> Vec<(String, u64)> {
+    let mut rows: Vec<_> = json["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| {
+            let id = f["id"].as_str().unwrap();
+            (
+                id.rsplit_once('-').unwrap().0.to_string(),
+                f["line"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+#[test]
+fn test_findings_move_with_the_fixes() {
+    let dir = repo(false);
+    let output = revet(
+        dir.path(),
+        &[
+            "--full",
+            "--fix",
+            "--no-baseline",
+            "--fail-on",
+            "never",
+            "--format",
+            "json",
+        ],
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    // The breakpoint is gone, and both queries moved up a line
+    assert!(!fs::read_to_string(dir.path().join("app.py"))
+        .unwrap()
+        .contains("breakpoint"));
+    assert_eq!(
+        lines(&json),
+        [("SQL".to_string(), 2), ("SQL".to_string(), 6)]
+    );
+
+    // The last run's finding points at the query too
+    let sql = json["findings"][0]["id"].as_str().unwrap();
+    let explain = revet(dir.path(), &["explain", sql]);
+    let stdout = String::from_utf8_lossy(&explain.stdout);
+    assert!(stdout.contains("This Finding: app.py:2"), "{}", stdout);
+}
+
+#[test]
+fn test_auto_reconcile_moves_the_baseline_anchors() {
+    let dir = repo(true);
+    assert!(revet(dir.path(), &["baseline"]).status.success());
+    let before = Baseline::load(dir.path()).unwrap().unwrap();
+    assert_eq!(before.count, 3);
+
+    let output = revet(dir.path(), &["--full", "--fix", "--fail-on", "never"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("baseline entry for app.py:2 dropped, a fix deleted its line"),
+        "{}",
+        stderr
+    );
+
+    let after = Baseline::load(dir.path()).unwrap().unwrap();
+    assert_eq!(after.count, 2);
+    let mut anchors: Vec<Option<usize>> = after.entries.iter().map(|e| e.line).collect();
+    anchors.sort();
+    assert_eq!(anchors, [Some(2), Some(6)]);
+}
+
+#[test]
+fn test_baseline_is_left_alone_without_auto_reconcile() {
+    let dir = repo(false);
+    assert!(revet(dir.path(), &["baseline"]).status.success());
+    let output = revet(dir.path(), &["--full", "--fix", "--fail-on", "never"]);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("reconciled"));
+    assert_eq!(Baseline::load(dir.path()).unwrap().unwrap().count, 3);
+}
@@ -0,0 +1,60 @@
+use clap::Parser;
+use revet_cli::commands::review;
+use revet_cli::Cli;
+use revet_core::SkipReason;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn cli(args: &[&str]) -> Cli {
+    let mut argv = vec!["revet"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+/// Copy a `tests/fixtures/generated_<policy>` fixture, so reviews don't
+/// write their cache into the source tree
+fn fixture(policy: &str) -> TempDir {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures")
+        .join(format!("generated_{}", policy));
+    let dir = TempDir::new().unwrap();
+    for entry in std::fs::read_dir(src).unwrap() {
+        let path = entry.unwrap().path();
+        std::fs::copy(&path, dir.path().join(path.file_name().unwrap())).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn test_skip_parses_but_reports_nothing() {
+    let dir = fixture("skip");
+    let run = review::analyze(dir.path(), &cli(&["--full", "--coverage-report=full"])).unwrap();
+    assert!(run.findings.is_empty(), "{:?}", run.findings);
+
+    let ledger = run.ledger.unwrap();
+    let pb = dir.path().join("api_pb2.py");
+    assert!(ledger.is_parsed(&pb));
+    assert!(ledger.is_generated(&pb));
+    assert_eq!(ledger.skip_reason(&pb), Some(SkipReason::Generated));
+    assert_eq!(ledger.skip_reason(&dir.path().join("service.py")), None);
+}
+
+#[test]
+fn test_info_only_is_outside_the_gate() {
+    let dir = fixture("info_only");
+    let run = review::analyze(dir.path(), &cli(&["--full"])).unwrap();
+    assert_eq!(run.findings.len(), 2);
+    assert!(run.findings.iter().all(|f| f.generated));
+    assert_eq!(run.summary.generated, 2);
+    assert_eq!(run.summary.info, 0);
+    assert!(!run.summary.exceeds_threshold("info"));
+}
+
+#[test]
+fn test_analyze_fails_the_gate() {
+    let dir = fixture("analyze");
+    let run = review::analyze(dir.path(), &cli(&["--full"])).unwrap();
+    assert_eq!(run.summary.errors, 2);
+    assert_eq!(run.summary.generated, 0);
+    assert!(run.summary.exceeds_threshold("error"));
+}
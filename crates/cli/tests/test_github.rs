@@ -45,3 +45,30 @@ fn info_finding() {
         "::notice file=README.md,line=1,title=SEC-001::Hardcoded secret detected"
     );
 }
+
+#[test]
+fn finding_with_column_range() {
+    let mut f = make_finding(Severity::Warning, "src/app.ts", 3);
+    f.column = Some(20);
+    f.end_column = Some(33);
+    let out = format_finding(&f, Path::new("/repo"));
+    assert_eq!(
+        out,
+        "::warning file=src/app.ts,line=3,col=20,endColumn=33,title=SEC-001::Hardcoded secret detected"
+    );
+}
+
+#[test]
+fn repository_level_finding_is_a_workflow_notice() {
+    let f = Finding {
+        id: "REPO-002".to_string(),
+        severity: Severity::Error,
+        message: "Required check `test`, 100% missing".to_string(),
+        ..Default::default()
+    };
+    let out = format_finding(&f, Path::new("/repo"));
+    assert_eq!(
+        out,
+        "::notice title=REPO-002::Required check `test`, 100%25 missing"
+    );
+}
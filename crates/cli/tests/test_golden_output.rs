@@ -0,0 +1,387 @@
+//! Golden output tests: every review format rendered from every findings
+//! set in `tests/golden/fixtures`, compared byte for byte with the report
+//! committed under `tests/golden/<fixture>/`.
+//!
+//! Reports are rendered redacted (see `revet_cli::output::sink`), so
+//! elapsed time, run IDs and the revet version print as placeholders.
+//! Findings sit under a fixed `/repo` root. JSON reports must read back
+//! into the document types they were written from, SARIF reports must
+//! satisfy the schema and Checkstyle and JUnit reports must parse.
+//!
+//! After an intentional output change, `cargo xtask bless-output` rewrites
+//! the goldens (the same test with `REVET_BLESS=1`), so the diff shows what
+//! changed in each format.
+
+use revet_cli::output::json::JsonOutput;
+use revet_cli::output::{make_reporter, Format, Out, RunEnd, RunStart};
+use revet_core::policy::{Action, PolicyReport, PolicyResult};
+use revet_core::{Finding, Locale, Reproducibility, ReviewSummary, Severity, SuppressedFinding};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+mod common;
+use common::sarif_schema_subset;
+
+/// Root the fixtures' relative paths are resolved against
+const ROOT: &str = "/repo";
+
+/// A canonical findings set and the rest of a run around it
+#[derive(Deserialize)]
+struct Fixture {
+    files_analyzed: usize,
+    #[serde(default)]
+    files_by_language: HashMap<String, usize>,
+    /// Paths relative to [`ROOT`]; empty for repository-level findings
+    #[serde(default)]
+    findings: Vec<Finding>,
+    /// Synthetic findings added after `findings`
+    #[serde(default)]
+    generate: usize,
+    #[serde(default)]
+    suppressed: Vec<Suppressed>,
+    #[serde(default)]
+    policies: Vec<Policy>,
+    #[serde(default)]
+    reproducibility: Option<Reproducibility>,
+    #[serde(default)]
+    run_id: Option<String>,
+    #[serde(default)]
+    elapsed_ms: u64,
+    #[serde(default = "passed")]
+    passed: bool,
+    /// `--format` names to render; all when unset
+    #[serde(default)]
+    formats: Option<Vec<String>>,
+}
+
+fn passed() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct Suppressed {
+    reason: String,
+    finding: Finding,
+}
+
+#[derive(Deserialize)]
+struct Policy {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    filter: String,
+    action: String,
+    condition: String,
+    count: usize,
+    passed: bool,
+}
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+fn rooted(mut finding: Finding) -> Finding {
+    if !finding.is_repository_level() {
+        finding.file = Path::new(ROOT).join(&finding.file);
+    }
+    finding
+}
+
+/// `count` findings over 40 files, cycling through rule prefixes and
+/// severities
+fn generated(count: usize) -> Vec<Finding> {
+    let prefixes = ["SEC", "SQL", "ERR"];
+    let severities = [Severity::Error, Severity::Warning, Severity::Info];
+    (0..count)
+        .map(|n| Finding {
+            id: format!("{}-{:03}", prefixes[n % 3], n + 1),
+            severity: severities[n % 3],
+            message: format!("Generated finding {}", n + 1),
+            file: PathBuf::from(format!("src/module_{:02}.py", n % 40)),
+            line: n / 40 + 1,
+            ..Default::default()
+        })
+        .collect()
+}
+
+impl Fixture {
+    fn load(path: &Path) -> Self {
+        let text = fs::read_to_string(path).unwrap();
+        serde_json::from_str(&text).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+    }
+
+    fn findings(&self) -> Vec<Finding> {
+        self.findings
+            .iter()
+            .cloned()
+            .chain(generated(self.generate))
+            .map(rooted)
+            .collect()
+    }
+
+    fn formats(&self) -> Vec<Format> {
+        Format::ALL
+            .into_iter()
+            .filter(|f| match &self.formats {
+                Some(names) => names.iter().any(|n| n == f.name()),
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Render the run in `format`, as the CLI would print it
+    fn render(&self, format: Format) -> String {
+        let findings = self.findings();
+        let suppressed: Vec<SuppressedFinding> = self
+            .suppressed
+            .iter()
+            .map(|s| SuppressedFinding {
+                finding: rooted(s.finding.clone()),
+                reason: s.reason.clone(),
+            })
+            .collect();
+        let count = |severity| findings.iter().filter(|f| f.severity == severity).count();
+        let summary = ReviewSummary {
+            errors: count(Severity::Error),
+            warnings: count(Severity::Warning),
+            info: count(Severity::Info),
+            files_analyzed: self.files_analyzed,
+            nodes_parsed: self.files_analyzed * 10,
+            files_by_language: self.files_by_language.clone(),
+            ..Default::default()
+        };
+        let policies = PolicyReport {
+            results: self
+                .policies
+                .iter()
+                .map(|p| PolicyResult {
+                    name: p.name.clone(),
+                    description: p.description.clone(),
+                    filter: p.filter.clone(),
+                    action: Action::parse(&p.action).unwrap(),
+                    condition: p.condition.clone(),
+                    count: p.count,
+                    passed: p.passed,
+                    matches: Vec::new(),
+                })
+                .collect(),
+        };
+
+        let out = Out::buffer().redacted();
+        let root = Path::new(ROOT);
+        let mut reporter = make_reporter(format, root, true, true, Locale::En, out.clone());
+        reporter.begin_run(&RunStart {
+            reproducibility: self.reproducibility.as_ref(),
+            ..Default::default()
+        });
+        for finding in &findings {
+            reporter.report_finding(finding, root);
+        }
+        for sf in &suppressed {
+            reporter.report_suppressed(sf, root);
+        }
+        reporter.end_run(&RunEnd {
+            policies: (!policies.is_empty()).then_some(&policies),
+            elapsed: Duration::from_millis(self.elapsed_ms),
+            run_id: self.run_id.as_deref(),
+            passed: self.passed,
+            ..RunEnd::new(&summary, &suppressed)
+        });
+        reporter.finalize();
+        out.contents()
+    }
+}
+
+fn extension(format: Format) -> &'static str {
+    match format {
+        Format::Json => "json",
+        Format::Sarif => "sarif",
+        Format::Markdown => "md",
+        Format::Checkstyle | Format::Junit(_) => "xml",
+        Format::Terminal | Format::Github | Format::GithubMatcher => "txt",
+    }
+}
+
+/// Check `report` against its format's contract
+fn validate(format: Format, report: &str) -> Result<(), String> {
+    match format {
+        Format::Json => {
+            let value: serde_json::Value =
+                serde_json::from_str(report).map_err(|e| e.to_string())?;
+            let document: JsonOutput =
+                serde_json::from_value(value.clone()).map_err(|e| e.to_string())?;
+            // Every field read back: nothing written outside the document types
+            if serde_json::to_value(&document).unwrap() != value {
+                return Err("does not round-trip through JsonOutput".to_string());
+            }
+            Ok(())
+        }
+        Format::Sarif => {
+            let value: serde_json::Value =
+                serde_json::from_str(report).map_err(|e| e.to_string())?;
+            let schema = sarif_schema_subset();
+            let result = schema.validate(&value);
+            if let Err(errors) = result {
+                let errors: Vec<String> = errors
+                    .map(|e| format!("{} at {}", e, e.instance_path))
+                    .collect();
+                return Err(errors.join("\n"));
+            }
+            Ok(())
+        }
+        Format::Checkstyle | Format::Junit(_) => roxmltree::Document::parse(report)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        Format::Terminal | Format::Github | Format::GithubMatcher | Format::Markdown => Ok(()),
+    }
+}
+
+/// The lines where `expected` and `actual` differ, with the line number of
+/// the first one and a few lines of context either side
+fn diff(expected: &str, actual: &str) -> String {
+    const CONTEXT: usize = 3;
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let prefix = expected
+        .iter()
+        .zip(&actual)
+        .take_while(|(e, a)| e == a)
+        .count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(e, a)| e == a)
+        .count();
+    let start = prefix.saturating_sub(CONTEXT);
+    let mut out = format!("@@ line {} @@\n", prefix + 1);
+    for line in &expected[start..prefix] {
+        out.push_str(&format!("  {}\n", line));
+    }
+    for line in &expected[prefix..expected.len() - suffix] {
+        out.push_str(&format!("- {}\n", line));
+    }
+    for line in &actual[prefix..actual.len() - suffix] {
+        out.push_str(&format!("+ {}\n", line));
+    }
+    let end = (actual.len() - suffix + CONTEXT).min(actual.len());
+    for line in &actual[actual.len() - suffix..end] {
+        out.push_str(&format!("  {}\n", line));
+    }
+    if expected.len() == actual.len() && prefix == expected.len() {
+        out.push_str("(the reports differ only in line endings)\n");
+    }
+    out
+}
+
+#[test]
+fn test_every_format_matches_its_golden_report() {
+    colored::control::set_override(false);
+    let bless = std::env::var_os("REVET_BLESS").is_some();
+
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(golden_dir().join("fixtures"))
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|e| e == "json"))
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty());
+
+    let mut failures = Vec::new();
+    for path in &fixtures {
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let fixture = Fixture::load(path);
+        for format in fixture.formats() {
+            let report = fixture.render(format);
+            let golden =
+                golden_dir()
+                    .join(&name)
+                    .join(format!("{}.{}", format.name(), extension(format)));
+            let at = format!("{}/{}", name, format.name());
+            if let Err(e) = validate(format, &report) {
+                failures.push(format!("{}: invalid report: {}\n{}", at, e, report));
+                continue;
+            }
+            if bless {
+                fs::create_dir_all(golden.parent().unwrap()).unwrap();
+                fs::write(&golden, &report).unwrap();
+                continue;
+            }
+            match fs::read_to_string(&golden) {
+                Ok(expected) if expected == report => {}
+                Ok(expected) => failures.push(format!(
+                    "{}: differs from {}\n{}",
+                    at,
+                    golden.display(),
+                    diff(&expected, &report)
+                )),
+                Err(_) => failures.push(format!("{}: no golden at {}", at, golden.display())),
+            }
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{}\n\nIf the change is intended, run `cargo xtask bless-output` and review the diff.",
+        failures.join("\n\n")
+    );
+}
+
+#[test]
+fn test_every_golden_has_a_fixture() {
+    for entry in fs::read_dir(golden_dir()).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        if name == "fixtures" {
+            continue;
+        }
+        let fixture = golden_dir().join("fixtures").join(format!("{}.json", name));
+        assert!(
+            fixture.exists(),
+            "{} has no fixture; remove it or add {}",
+            path.display(),
+            fixture.display()
+        );
+        let formats = Fixture::load(&fixture).formats();
+        for golden in fs::read_dir(&path).unwrap() {
+            let golden = golden.unwrap().path();
+            let stem = golden.file_stem().unwrap().to_string_lossy().into_owned();
+            assert!(
+                formats.iter().any(|f| f.name() == stem),
+                "{} is not a format {} renders",
+                golden.display(),
+                name
+            );
+        }
+    }
+}
+
+#[test]
+fn test_redaction_only_touches_volatile_fields() {
+    let fixture = Fixture::load(&golden_dir().join("fixtures/medium.json"));
+    let terminal = fixture.render(Format::Terminal);
+    assert!(terminal.contains("Time: <elapsed>"), "{}", terminal);
+    assert!(
+        terminal.contains("Run log: revet log --show <run-id>"),
+        "{}",
+        terminal
+    );
+    assert!(!terminal.contains("4.3s"), "{}", terminal);
+
+    let sarif: serde_json::Value = serde_json::from_str(&fixture.render(Format::Sarif)).unwrap();
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["semanticVersion"], "<version>");
+    assert_eq!(
+        run["properties"]["reproducibility"]["revet_version"],
+        "<version>"
+    );
+    assert_eq!(run["properties"]["reproducibility"]["profile"], "standard");
+
+    // An unredacted buffer keeps them
+    let out = Out::buffer();
+    assert_eq!(out.elapsed(Duration::from_millis(4321)), "4.3s");
+    assert_eq!(out.run_id("abc"), "abc");
+    assert_eq!(out.version("1.2.3"), "1.2.3");
+}
@@ -0,0 +1,172 @@
+//! `revet hash-inputs` and `revet review --assert-hash`: the cache-hit and
+//! cache-miss flows of an Nx or Turborepo task
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+const SECRET: &str = "password = \"hunter2-Secret-Value\"\nprint(password)\n";
+
+/// `packages/api` depending on `packages/shared`, and `packages/web`;
+/// api and web each hold a hardcoded secret
+fn monorepo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    fs::write(
+        root.join(".revet.toml"),
+        "[[project]]\npath = \"packages/api\"\ndepends_on = [\"packages/shared\"]\n",
+    )
+    .unwrap();
+    for (project, body) in [
+        ("api", SECRET),
+        ("shared", "def helper():\n    return 1\n"),
+        ("web", SECRET),
+    ] {
+        let path = root.join("packages").join(project);
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+        fs::write(path.join("main.py"), body).unwrap();
+    }
+    dir
+}
+
+fn revet(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(args)
+        .arg("--print-exit-reason")
+        .current_dir(dir)
+        .env_remove("REVET_LOCALE")
+        .env_remove("GITHUB_ACTIONS")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+/// The digest, taken with the task's profile flags
+fn hash_inputs(dir: &Path) -> String {
+    let output = revet(
+        dir,
+        &[
+            "hash-inputs",
+            "--project",
+            "packages/api",
+            "--fail-on",
+            "never",
+        ],
+    );
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let digest = String::from_utf8(output.stdout).unwrap().trim().to_string();
+    assert_eq!(digest.len(), 64, "{}", digest);
+    digest
+}
+
+/// The cached task: review the project, trusting the digest taken before
+fn task(dir: &Path, digest: &str) -> Output {
+    revet(
+        dir,
+        &[
+            "review",
+            "--project",
+            "packages/api",
+            "--assert-hash",
+            digest,
+            "--format",
+            "json",
+            "--fail-on",
+            "never",
+        ],
+    )
+}
+
+#[test]
+fn test_cache_hit_and_miss_flows() {
+    let repo = monorepo();
+    let dir = repo.path();
+
+    // First run: the runner hashes the inputs, then runs the task
+    let digest = hash_inputs(dir);
+    let output = task(dir, &digest);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let files: Vec<&str> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["file"].as_str().unwrap())
+        .collect();
+    assert!(!files.is_empty());
+    assert!(
+        files.iter().all(|f| f.contains("packages/api")),
+        "{:?}",
+        files
+    );
+
+    // Cache hit: nothing the project depends on changed
+    fs::write(dir.join("packages/web/main.py"), "print('web')\n").unwrap();
+    assert_eq!(hash_inputs(dir), digest);
+
+    // Cache miss: a dependency changed after the runner hashed the inputs
+    fs::write(
+        dir.join("packages/shared/main.py"),
+        "def helper():\n    return 2\n",
+    )
+    .unwrap();
+    let output = task(dir, &digest);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(5), "{}", stderr);
+    assert!(output.stdout.is_empty());
+    let fresh = hash_inputs(dir);
+    assert_ne!(fresh, digest);
+    assert_eq!(
+        stderr.lines().last().unwrap(),
+        format!("exit=5 reason=stale_inputs digest={}", fresh)
+    );
+
+    // The runner re-executes with the new digest
+    assert_eq!(task(dir, &fresh).status.code(), Some(0));
+}
+
+#[test]
+fn test_json_lists_the_inputs() {
+    let repo = monorepo();
+    let output = revet(
+        repo.path(),
+        &[
+            "hash-inputs",
+            "--project",
+            "packages/api",
+            "--fail-on",
+            "never",
+            "--format",
+            "json",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["digest"].as_str().unwrap(), hash_inputs(repo.path()));
+    let inputs = &report["inputs"];
+    assert_eq!(inputs["project"], "packages/api");
+    assert_eq!(inputs["depends_on"][0], "packages/shared");
+    assert!(inputs["files"]["packages/shared/main.py"].is_string());
+    assert!(inputs["files"]["packages/web/main.py"].is_null());
+    assert!(inputs["analyzers"]["security"].is_string());
+}
+
+#[test]
+fn test_project_is_required_and_checked() {
+    let repo = monorepo();
+    let output = revet(repo.path(), &["hash-inputs"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    fs::create_dir_all(repo.path().join("docs")).unwrap();
+    let output = revet(repo.path(), &["hash-inputs", "--project", "docs"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not a project"));
+}
@@ -0,0 +1,525 @@
+//! `revet issues sync`: which findings get an issue, which issues are
+//! closed, re-runs, dry runs and failed calls, against a mock tracker; and
+//! the GitHub tracker end to end against a mock API (pagination, a rate
+//! limit, create and close)
+
+use anyhow::{bail, Result};
+use revet_cli::commands::issues::run;
+use revet_cli::issues::{fingerprint, DryRun, Issue, NewIssue, Selection, Tracker};
+use revet_cli::run_log::{RunFinding, RunLog};
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use tiny_http::{Header, Response, Server};
+
+const SQL: (&str, &str, &str, &str) = (
+    "SQL-001",
+    "error",
+    "db.py",
+    "SQL query built with string formatting",
+);
+const SECRET: (&str, &str, &str, &str) = ("SEC-001", "error", "settings.py", "AWS access key");
+const COMPLEX: (&str, &str, &str, &str) =
+    ("CMPLX-001", "warning", "big.py", "Function too complex");
+
+fn finding((id, severity, file, message): (&str, &str, &str, &str)) -> Value {
+    json!({
+        "id": id,
+        "severity": severity,
+        "message": message,
+        "file": file,
+        "line": 3,
+        "suppressed": false,
+        "suppression_reason": null,
+    })
+}
+
+fn run_log(id: u64, branch: &str, full: bool, findings: &[(&str, &str, &str, &str)]) -> Value {
+    json!({
+        "id": id.to_string(),
+        "version": "0.0.0",
+        "timestamp": id / 1000,
+        "duration_secs": 1.0,
+        "files_analyzed": 3,
+        "nodes_parsed": 10,
+        "branch": branch,
+        "full": full,
+        "summary": { "errors": 0, "warnings": 0, "info": 0, "suppressed": 0 },
+        "findings": findings.iter().map(|f| finding(*f)).collect::<Vec<_>>(),
+    })
+}
+
+/// Full runs on main, newest first
+fn history(runs: &[&[(&str, &str, &str, &str)]]) -> Vec<RunLog> {
+    runs.iter()
+        .enumerate()
+        .map(|(i, findings)| {
+            let id = 1_700_000_000_000 - i as u64 * 1000;
+            serde_json::from_value(run_log(id, "main", true, findings)).unwrap()
+        })
+        .collect()
+}
+
+fn fp(f: (&str, &str, &str, &str)) -> String {
+    let finding: RunFinding = serde_json::from_value(finding(f)).unwrap();
+    fingerprint(&finding)
+}
+
+fn selection(min_age_runs: usize) -> Selection {
+    Selection {
+        min_severity: "error".to_string(),
+        min_age_runs,
+        rules: Vec::new(),
+    }
+}
+
+/// Issues in memory; `fail_create` rejects creating issues with that title
+/// prefix
+#[derive(Default)]
+struct MockTracker {
+    open: RefCell<Vec<Issue>>,
+    calls: RefCell<Vec<String>>,
+    fail_create: Option<&'static str>,
+}
+
+impl Tracker for MockTracker {
+    fn name(&self) -> &str {
+        "Mock"
+    }
+
+    fn open_issues(&self) -> Result<Vec<Issue>> {
+        self.calls.borrow_mut().push("list".to_string());
+        Ok(self.open.borrow().clone())
+    }
+
+    fn create(&self, issue: &NewIssue) -> Result<Issue> {
+        self.calls
+            .borrow_mut()
+            .push(format!("create {}", issue.title));
+        if self.fail_create.is_some_and(|p| issue.title.starts_with(p)) {
+            bail!("422 Unprocessable Entity");
+        }
+        let mut open = self.open.borrow_mut();
+        let created = Issue {
+            key: format!("#{}", open.len() + 1),
+            title: issue.title.clone(),
+            fingerprint: revet_cli::issues::marker_of(&issue.body)
+                .unwrap()
+                .to_string(),
+            url: String::new(),
+        };
+        open.push(created.clone());
+        Ok(created)
+    }
+
+    fn close(&self, issue: &Issue, _comment: &str) -> Result<()> {
+        self.calls.borrow_mut().push(format!("close {}", issue.key));
+        self.open.borrow_mut().retain(|i| i.key != issue.key);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_creates_issues_for_findings_that_persist() {
+    let tracker = MockTracker::default();
+    // SQL in all three runs, the secret only in the latest two; the
+    // warning is below min_severity
+    let history = history(&[
+        &[SQL, SECRET, COMPLEX],
+        &[SQL, SECRET, COMPLEX],
+        &[SQL, COMPLEX],
+    ]);
+
+    let report = run(&tracker, &history, &selection(3)).unwrap();
+
+    assert_eq!(report.tracked, 1);
+    assert_eq!(report.sync.created.len(), 1);
+    assert_eq!(report.sync.created[0].fingerprint, fp(SQL));
+    assert_eq!(
+        report.sync.created[0].title,
+        "[revet] SQL: SQL query built with string formatting"
+    );
+    assert!(report.sync.closed.is_empty());
+    assert!(report.sync.failures.is_empty());
+
+    // Two runs are enough for the secret
+    let report = run(&tracker, &history, &selection(2)).unwrap();
+    assert_eq!(report.tracked, 2);
+    assert_eq!(report.sync.created.len(), 1);
+    assert_eq!(report.sync.created[0].fingerprint, fp(SECRET));
+}
+
+#[test]
+fn test_too_few_runs_create_nothing() {
+    let tracker = MockTracker::default();
+    let history = history(&[&[SQL], &[SQL]]);
+    let report = run(&tracker, &history, &selection(3)).unwrap();
+    assert_eq!(report.tracked, 0);
+    assert!(report.sync.created.is_empty());
+}
+
+#[test]
+fn test_rerun_is_idempotent() {
+    let tracker = MockTracker::default();
+    let history = history(&[&[SQL, SECRET], &[SQL, SECRET], &[SQL, SECRET]]);
+
+    let first = run(&tracker, &history, &selection(3)).unwrap();
+    assert_eq!(first.sync.created.len(), 2);
+
+    tracker.calls.borrow_mut().clear();
+    let second = run(&tracker, &history, &selection(3)).unwrap();
+    assert!(second.sync.created.is_empty());
+    assert!(second.sync.closed.is_empty());
+    assert_eq!(*tracker.calls.borrow(), vec!["list"]);
+}
+
+#[test]
+fn test_issues_of_gone_findings_are_closed() {
+    let tracker = MockTracker::default();
+    let before = history(&[&[SQL, SECRET], &[SQL, SECRET], &[SQL, SECRET]]);
+    run(&tracker, &before, &selection(3)).unwrap();
+
+    // The secret was removed in the latest run
+    let after = history(&[&[SQL], &[SQL, SECRET], &[SQL, SECRET], &[SQL, SECRET]]);
+    let report = run(&tracker, &after, &selection(3)).unwrap();
+
+    assert!(report.sync.created.is_empty());
+    assert_eq!(report.sync.closed.len(), 1);
+    assert_eq!(report.sync.closed[0].fingerprint, fp(SECRET));
+    let open = tracker.open.borrow();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].fingerprint, fp(SQL));
+}
+
+#[test]
+fn test_dry_run_changes_nothing() {
+    let tracker = MockTracker::default();
+    tracker.open.borrow_mut().push(Issue {
+        key: "#9".to_string(),
+        title: "[revet] SEC: AWS access key".to_string(),
+        fingerprint: fp(SECRET),
+        url: String::new(),
+    });
+    let history = history(&[&[SQL], &[SQL], &[SQL]]);
+
+    let report = run(&DryRun(&tracker), &history, &selection(3)).unwrap();
+
+    assert_eq!(report.sync.created.len(), 1);
+    assert_eq!(report.sync.created[0].key, "");
+    assert_eq!(report.sync.closed.len(), 1);
+    assert_eq!(report.sync.closed[0].key, "#9");
+    assert_eq!(*tracker.calls.borrow(), vec!["list"]);
+    assert_eq!(tracker.open.borrow().len(), 1);
+}
+
+#[test]
+fn test_failed_call_is_reported_and_the_rest_goes_on() {
+    let tracker = MockTracker {
+        fail_create: Some("[revet] SEC"),
+        ..Default::default()
+    };
+    let history = history(&[&[SECRET, SQL], &[SECRET, SQL], &[SECRET, SQL]]);
+
+    let report = run(&tracker, &history, &selection(3)).unwrap();
+
+    assert_eq!(report.sync.created.len(), 1);
+    assert_eq!(report.sync.created[0].fingerprint, fp(SQL));
+    assert_eq!(report.sync.failures.len(), 1);
+    assert!(
+        report.sync.failures[0].contains("create \"[revet] SEC: AWS access key\": 422"),
+        "{:?}",
+        report.sync.failures
+    );
+}
+
+#[test]
+fn test_rule_filter() {
+    let tracker = MockTracker::default();
+    let history = history(&[&[SQL, SECRET], &[SQL, SECRET], &[SQL, SECRET]]);
+    let selection = Selection {
+        rules: vec!["SEC".to_string()],
+        ..selection(3)
+    };
+    let report = run(&tracker, &history, &selection).unwrap();
+    assert_eq!(report.sync.created.len(), 1);
+    assert_eq!(report.sync.created[0].fingerprint, fp(SECRET));
+}
+
+// ── GitHub, end to end ───────────────────────────────────────────
+
+/// A repository whose state directory holds full runs on main (SQL in
+/// all three, the secret only in older ones) and a diff run that must not
+/// count
+fn repo_with_runs() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let runs = dir.path().join(".revet-cache").join("runs");
+    fs::create_dir_all(&runs).unwrap();
+    let logs = [
+        run_log(1_700_000_003_000, "main", true, &[SQL]),
+        run_log(1_700_000_002_000, "main", true, &[SQL, SECRET]),
+        run_log(1_700_000_001_000, "main", true, &[SQL, SECRET]),
+        run_log(1_700_000_004_000, "main", false, &[]),
+        run_log(1_700_000_005_000, "feature", true, &[]),
+    ];
+    for log in logs {
+        fs::write(
+            runs.join(format!("{}.json", log["id"].as_str().unwrap())),
+            log.to_string(),
+        )
+        .unwrap();
+    }
+    dir
+}
+
+/// Serve a GitHub API: open issues over two pages (the second
+/// rate-limited once), one of them a pull request; record each request
+fn github_api() -> (String, Arc<Mutex<Vec<String>>>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&requests);
+    let secret_fp = fp(SECRET);
+    let link_base = base.clone();
+    std::thread::spawn(move || {
+        let mut limited = false;
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            let _ = request.as_reader().read_to_string(&mut body);
+            let line = format!("{} {}", request.method(), request.url());
+            log.lock().unwrap().push(line.clone());
+            let json_header = Header::from_bytes("Content-Type", "application/json").unwrap();
+            let response = if line.starts_with("GET /repos/acme/api/issues?state=open") {
+                assert!(line.contains("labels=revet"), "{}", line);
+                let next = format!("<{}/repos/acme/api/issues?page=2>; rel=\"next\"", link_base);
+                Response::from_string(
+                    json!([{ "number": 3, "title": "A pull request", "body": "", "pull_request": {} }])
+                        .to_string(),
+                )
+                .with_header(json_header)
+                .with_header(Header::from_bytes("Link", next).unwrap())
+            } else if line == "GET /repos/acme/api/issues?page=2" && !limited {
+                limited = true;
+                Response::from_string("slow down")
+                    .with_status_code(429)
+                    .with_header(Header::from_bytes("Retry-After", "0").unwrap())
+            } else if line == "GET /repos/acme/api/issues?page=2" {
+                Response::from_string(
+                    json!([{
+                        "number": 7,
+                        "title": "[revet] SEC: AWS access key",
+                        "html_url": "https://github.com/acme/api/issues/7",
+                        "body": format!("AWS access key\n\nrevet-fingerprint: {}\n", secret_fp),
+                    }])
+                    .to_string(),
+                )
+                .with_header(json_header)
+            } else if line == "POST /repos/acme/api/issues" {
+                let sent: Value = serde_json::from_str(&body).unwrap();
+                assert_eq!(sent["labels"], json!(["revet"]));
+                assert!(sent["body"]
+                    .as_str()
+                    .unwrap()
+                    .contains("revet-fingerprint: "));
+                Response::from_string(
+                    json!({ "number": 42, "html_url": "https://github.com/acme/api/issues/42" })
+                        .to_string(),
+                )
+                .with_status_code(201)
+                .with_header(json_header)
+            } else {
+                Response::from_string("{}").with_header(json_header)
+            };
+            let _ = request.respond(response);
+        }
+    });
+    (base, requests)
+}
+
+fn revet(repo: &Path, api: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(["issues", "sync"])
+        .arg(repo)
+        .args(["--tracker", "github"])
+        .args(args)
+        .env_remove("REVET_DATA_DIR")
+        .env("GITHUB_TOKEN", "test-token")
+        .env("GITHUB_REPOSITORY", "acme/api")
+        .env("GITHUB_API_URL", api)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_github_sync_end_to_end() {
+    let repo = repo_with_runs();
+    let (api, requests) = github_api();
+
+    let output = revet(repo.path(), &api, &[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(
+        stderr.contains("3 full runs on main; 1 tracked findings"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("Created #42 [revet] SQL: SQL query built with string formatting"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("Closed #7 [revet] SEC: AWS access key"),
+        "{}",
+        stderr
+    );
+
+    let requests = requests.lock().unwrap();
+    let page2 = requests
+        .iter()
+        .filter(|r| *r == "GET /repos/acme/api/issues?page=2")
+        .count();
+    assert_eq!(page2, 2, "{:?}", requests);
+    assert!(requests.contains(&"POST /repos/acme/api/issues".to_string()));
+    assert!(requests.contains(&"POST /repos/acme/api/issues/7/comments".to_string()));
+    assert!(requests.contains(&"PATCH /repos/acme/api/issues/7".to_string()));
+}
+
+#[test]
+fn test_github_dry_run_only_reads() {
+    let repo = repo_with_runs();
+    let (api, requests) = github_api();
+
+    let output = revet(repo.path(), &api, &["--dry-run"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(
+        stderr.contains("Would create [revet] SQL: SQL query built with string formatting"),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("Would close #7"), "{}", stderr);
+    assert!(
+        requests
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|r| r.starts_with("GET ")),
+        "{:?}",
+        requests
+    );
+}
+
+#[test]
+fn test_missing_token_is_an_environment_error() {
+    let repo = repo_with_runs();
+    let output = Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(["issues", "sync"])
+        .arg(repo.path())
+        .args(["--tracker", "github"])
+        .env_remove("REVET_DATA_DIR")
+        .env_remove("GITHUB_TOKEN")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("GITHUB_TOKEN"));
+}
+
+#[test]
+fn test_jira_sync_end_to_end() {
+    let repo = repo_with_runs();
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", server.server_addr().to_ip().unwrap());
+    fs::write(
+        repo.path().join(".revet.toml"),
+        format!("[issues.jira]\nurl = \"{}\"\nproject = \"SEC\"\n", base),
+    )
+    .unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&requests);
+    let secret_fp = fp(SECRET);
+    std::thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            let _ = request.as_reader().read_to_string(&mut body);
+            let url = request.url().to_string();
+            let path = url.split('?').next().unwrap_or_default().to_string();
+            log.lock()
+                .unwrap()
+                .push(format!("{} {} {}", request.method(), path, body));
+            // Search results come one per page, two in all
+            let answer = if path == "/rest/api/2/search" && url.contains("startAt=0") {
+                json!({ "total": 2, "issues": [{
+                    "key": "SEC-7",
+                    "fields": {
+                        "summary": "[revet] SEC: AWS access key",
+                        "description": format!("AWS access key\n\nrevet-fingerprint: {}", secret_fp),
+                    },
+                }]})
+            } else if path == "/rest/api/2/search" {
+                json!({ "total": 2, "issues": [{
+                    "key": "SEC-5",
+                    "fields": { "summary": "Filed by hand", "description": "no marker" },
+                }]})
+            } else if path == "/rest/api/2/issue" {
+                json!({ "key": "SEC-8" })
+            } else if request.method().as_str() == "GET" && path.ends_with("/transitions") {
+                json!({ "transitions": [
+                    { "id": "11", "name": "In Progress" },
+                    { "id": "31", "name": "Done" },
+                ]})
+            } else {
+                json!({})
+            };
+            let _ = request.respond(Response::from_string(answer.to_string()));
+        }
+    });
+
+    let output = Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(["issues", "sync"])
+        .arg(repo.path())
+        .args(["--tracker", "jira"])
+        .env_remove("REVET_DATA_DIR")
+        .env("JIRA_EMAIL", "bot@acme.test")
+        .env("JIRA_API_TOKEN", "test-token")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(stderr.contains("Created SEC-8"), "{}", stderr);
+    assert!(stderr.contains("Closed SEC-7"), "{}", stderr);
+
+    let requests = requests.lock().unwrap();
+    let searches = requests
+        .iter()
+        .filter(|r| r.starts_with("GET /rest/api/2/search"))
+        .count();
+    assert_eq!(searches, 2, "{:?}", requests);
+    let create = requests
+        .iter()
+        .find(|r| r.starts_with("POST /rest/api/2/issue "))
+        .expect("issue created");
+    assert!(create.contains("\"labels\":[\"revet\"]"), "{}", create);
+    assert!(create.contains("\"key\":\"SEC\""), "{}", create);
+    assert!(requests
+        .iter()
+        .any(|r| r.starts_with("POST /rest/api/2/issue/SEC-7/comment ")));
+    assert!(
+        requests.iter().any(
+            |r| r.starts_with("POST /rest/api/2/issue/SEC-7/transitions ")
+                && r.contains("\"id\":\"31\"")
+        ),
+        "{:?}",
+        requests
+    );
+    assert!(
+        !requests.iter().any(|r| r.contains("SEC-5")),
+        "{:?}",
+        requests
+    );
+}
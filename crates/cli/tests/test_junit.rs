@@ -0,0 +1,325 @@
+//! JUnit XML output: the emitted document parses back to one testsuite per
+//! analyzer prefix and one testcase per file, with the findings it was
+//! written from
+
+use revet_cli::output::junit::{InfoFindings, JunitFormatter};
+use revet_cli::output::Reporter;
+use revet_core::{Finding, RevetConfig, Severity};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+fn make_finding(id: &str, severity: Severity, message: &str, file: &str, line: usize) -> Finding {
+    Finding {
+        id: id.to_string(),
+        severity,
+        message: message.to_string(),
+        file: PathBuf::from(file),
+        line,
+        ..Default::default()
+    }
+}
+
+fn render(findings: &[Finding], info: InfoFindings) -> String {
+    let repo = Path::new("/repo");
+    let mut formatter = JunitFormatter::new(repo.to_path_buf()).with_info(info);
+    for finding in findings {
+        formatter.report_finding(finding, repo);
+    }
+    formatter.text().unwrap()
+}
+
+#[derive(Debug, PartialEq)]
+struct Case {
+    suite: String,
+    name: String,
+    classname: String,
+    /// `(message, type, text)` of every `<failure>`
+    failures: Vec<(String, String, String)>,
+    skipped: bool,
+    system_out: Option<String>,
+}
+
+fn parse(xml: &str) -> Vec<Case> {
+    let doc = roxmltree::Document::parse(xml).unwrap_or_else(|e| panic!("{}\n{}", e, xml));
+    let root = doc.root_element();
+    assert_eq!(root.tag_name().name(), "testsuites");
+    let mut cases = Vec::new();
+    let (mut tests, mut failures, mut skipped) = (0, 0, 0);
+    for suite in root.children().filter(|n| n.is_element()) {
+        assert_eq!(suite.tag_name().name(), "testsuite");
+        let name = suite.attribute("name").unwrap();
+        let count = |attr: &str| suite.attribute(attr).unwrap().parse::<usize>().unwrap();
+        let start = cases.len();
+        for case in suite.children().filter(|n| n.is_element()) {
+            assert_eq!(case.tag_name().name(), "testcase");
+            let children: Vec<_> = case.children().filter(|n| n.is_element()).collect();
+            cases.push(Case {
+                suite: name.to_string(),
+                name: case.attribute("name").unwrap().to_string(),
+                classname: case.attribute("classname").unwrap().to_string(),
+                failures: children
+                    .iter()
+                    .filter(|n| n.has_tag_name("failure"))
+                    .map(|n| {
+                        (
+                            n.attribute("message").unwrap().to_string(),
+                            n.attribute("type").unwrap().to_string(),
+                            n.text().unwrap().to_string(),
+                        )
+                    })
+                    .collect(),
+                skipped: children.iter().any(|n| n.has_tag_name("skipped")),
+                system_out: children
+                    .iter()
+                    .find(|n| n.has_tag_name("system-out"))
+                    .map(|n| n.text().unwrap().to_string()),
+            });
+        }
+        let suite_cases = &cases[start..];
+        assert_eq!(count("tests"), suite_cases.len(), "{}", xml);
+        let failed = suite_cases
+            .iter()
+            .filter(|c| !c.failures.is_empty())
+            .count();
+        assert_eq!(count("failures"), failed, "{}", xml);
+        let skips = suite_cases.iter().filter(|c| c.skipped).count();
+        assert_eq!(count("skipped"), skips, "{}", xml);
+        tests += suite_cases.len();
+        failures += failed;
+        skipped += skips;
+    }
+    let count = |attr: &str| root.attribute(attr).unwrap().parse::<usize>().unwrap();
+    assert_eq!(
+        (count("tests"), count("failures"), count("skipped")),
+        (tests, failures, skipped)
+    );
+    cases
+}
+
+#[test]
+fn test_suites_per_prefix_and_cases_per_file() {
+    let mut with_suggestion = make_finding(
+        "SEC-001",
+        Severity::Error,
+        "Hardcoded AWS key",
+        "/repo/src/config.py",
+        42,
+    );
+    with_suggestion.suggestion = Some("Read the key from the environment".to_string());
+    let findings = vec![
+        with_suggestion,
+        make_finding(
+            "SQL-002",
+            Severity::Warning,
+            "Query built by concatenation",
+            "/repo/src/db.py",
+            3,
+        ),
+        make_finding(
+            "SEC-004",
+            Severity::Warning,
+            "Weak hash",
+            "/repo/src/config.py",
+            7,
+        ),
+        make_finding(
+            "SEC-001",
+            Severity::Error,
+            "Hardcoded token",
+            "/repo/src/db.py",
+            1,
+        ),
+    ];
+
+    let xml = render(&findings, InfoFindings::Passed);
+    assert!(
+        xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"),
+        "{}",
+        xml
+    );
+    let cases = parse(&xml);
+    let names: Vec<(&str, &str)> = cases
+        .iter()
+        .map(|c| (c.suite.as_str(), c.name.as_str()))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("SEC", "src/config.py"),
+            ("SEC", "src/db.py"),
+            ("SQL", "src/db.py"),
+        ]
+    );
+    assert_eq!(cases[0].classname, "revet.SEC");
+    assert_eq!(cases[0].failures.len(), 2);
+
+    let (message, kind, text) = &cases[0].failures[0];
+    assert_eq!(message, "Hardcoded AWS key");
+    assert_eq!(kind, "SEC-001");
+    assert_eq!(
+        text,
+        "error SEC-001: Hardcoded AWS key\nat src/config.py:42\nSuggestion: Read the key from the environment"
+    );
+    assert_eq!(
+        cases[0].failures[1].2,
+        "warning SEC-004: Weak hash\nat src/config.py:7"
+    );
+}
+
+#[test]
+fn test_info_findings_pass_or_skip() {
+    let findings = vec![
+        make_finding(
+            "CMPLX-001",
+            Severity::Info,
+            "Function is long",
+            "/repo/a.py",
+            90,
+        ),
+        make_finding(
+            "CMPLX-002",
+            Severity::Warning,
+            "Too complex",
+            "/repo/b.py",
+            3,
+        ),
+        make_finding(
+            "CMPLX-001",
+            Severity::Info,
+            "Function is long",
+            "/repo/b.py",
+            40,
+        ),
+    ];
+
+    let passed = parse(&render(&findings, InfoFindings::Passed));
+    assert_eq!(passed.len(), 2);
+    assert!(passed[0].failures.is_empty());
+    assert!(!passed[0].skipped);
+    assert_eq!(
+        passed[0].system_out.as_deref(),
+        Some("info CMPLX-001: Function is long at a.py:90")
+    );
+
+    let skipped = parse(&render(&findings, InfoFindings::Skipped));
+    assert!(skipped[0].skipped);
+    assert!(skipped[0].failures.is_empty());
+    // A file with failures fails, its info findings listed alongside
+    assert!(!skipped[1].skipped);
+    assert_eq!(skipped[1].failures.len(), 1);
+    assert_eq!(
+        skipped[1].system_out.as_deref(),
+        Some("info CMPLX-001: Function is long at b.py:40")
+    );
+}
+
+#[test]
+fn test_cdata_end_and_unicode_survive() {
+    let message = "Template ends in ]]> then ]]]]> — naïve 日本語 🚀 <tag> & \"q\"";
+    let mut finding = make_finding(
+        "TYPE-001",
+        Severity::Warning,
+        message,
+        "/repo/src/ファイル ]]>.rs",
+        5,
+    );
+    finding.suggestion = Some("Escape `]]>` as `]]&gt;`\u{7}".to_string());
+    let info = make_finding("TYPE-002", Severity::Info, message, "/repo/src/x.rs", 1);
+
+    let xml = render(&[finding, info], InfoFindings::Passed);
+    let cases = parse(&xml);
+    assert_eq!(cases[0].name, "src/ファイル ]]>.rs");
+    let (parsed_message, _, text) = &cases[0].failures[0];
+    assert_eq!(parsed_message, message);
+    assert_eq!(
+        text,
+        &format!(
+            "warning TYPE-001: {}\nat src/ファイル ]]>.rs:5\nSuggestion: Escape `]]>` as `]]&gt;`",
+            message
+        )
+    );
+    assert_eq!(
+        cases[1].system_out.as_deref(),
+        Some(format!("info TYPE-002: {} at src/x.rs:1", message).as_str())
+    );
+}
+
+#[test]
+fn test_repository_level_findings_come_first() {
+    let findings = vec![
+        make_finding(
+            "REPO-002",
+            Severity::Warning,
+            "No CODEOWNERS",
+            "/repo/app.py",
+            4,
+        ),
+        make_finding("REPO-001", Severity::Warning, "No LICENSE file", "", 0),
+    ];
+
+    let cases = parse(&render(&findings, InfoFindings::Passed));
+    assert_eq!(cases[0].name, "(repository)");
+    assert_eq!(
+        cases[0].failures[0].2,
+        "warning REPO-001: No LICENSE file\nat (repository)"
+    );
+    assert_eq!(cases[1].name, "app.py");
+
+    assert!(parse(&render(&[], InfoFindings::Skipped)).is_empty());
+}
+
+#[test]
+fn test_review_writes_junit_report() {
+    let repo = TempDir::new().unwrap();
+    fs::write(
+        repo.path().join("charge.py"),
+        "def charge(cursor, user_id):\n    cursor.execute(\"SELECT * FROM users WHERE id = \" + user_id)\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.path().join(".revet.toml"),
+        "[output.junit]\ninfo = \"skipped\"\n",
+    )
+    .unwrap();
+    let out = TempDir::new().unwrap();
+    let report = out.path().join("revet-junit.xml");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(["--full", "--no-baseline", "--format"])
+        .arg(format!("junit={}", report.display()))
+        .arg("review")
+        .arg(repo.path())
+        .env_remove("REVET_LOCALE")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Report:"), "{}", stderr);
+
+    let cases = parse(&fs::read_to_string(&report).unwrap());
+    assert!(
+        cases
+            .iter()
+            .any(|c| c.name == "charge.py" && c.suite == "SQL" && !c.failures.is_empty()),
+        "{:?}",
+        cases
+    );
+}
+
+#[test]
+fn test_unknown_info_setting_is_a_config_error() {
+    let mut config = RevetConfig::default();
+    config.output.junit.info = "red".to_string();
+    let (errors, _) = config.validate();
+    assert!(
+        errors
+            .iter()
+            .any(|e| e
+                == "[output.junit] info = \"red\" is invalid. Must be one of: passed, skipped"),
+        "{:?}",
+        errors
+    );
+    assert!(RevetConfig::default().validate().0.is_empty());
+}
@@ -0,0 +1,115 @@
+//! `.revet-cache/last-run.json` and `revet explain <id>` reading it: the
+//! finding with the code around it, even once the file has changed, and
+//! the findings of the same prefix when the ID is not in the last run
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+const CHARGE: &str = "import db\n\n\ndef charge(cursor, user_id):\n    \
+    cursor.execute(\"SELECT * FROM users WHERE id = \" + user_id)\n    \
+    cursor.execute(\"DELETE FROM carts WHERE id = \" + user_id)\n";
+
+fn repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("charge.py"), CHARGE).unwrap();
+    dir
+}
+
+fn revet(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(args)
+        .current_dir(dir)
+        .env_remove("REVET_LOCALE")
+        .env_remove("REVET_DATA_DIR")
+        .env_remove("GITHUB_ACTIONS")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn review(dir: &Path) {
+    revet(dir, &["--full", "--no-baseline", "--fail-on", "never"]);
+}
+
+fn last_run(dir: &Path) -> serde_json::Value {
+    let json = fs::read_to_string(dir.join(".revet-cache/last-run.json")).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn test_review_records_findings_with_their_snippet() {
+    let dir = repo();
+    review(dir.path());
+
+    let run = last_run(dir.path());
+    assert!(run["run_id"].as_str().is_some_and(|id| !id.is_empty()));
+    let finding = &run["findings"][0];
+    assert_eq!(finding["id"], "SQL-001");
+    assert_eq!(finding["file"], "charge.py");
+    assert_eq!(finding["line"], 5);
+    assert!(finding["suggestion"].is_string(), "{}", finding);
+    // Three lines either side, clamped to the file
+    let lines: Vec<u64> = finding["snippet"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| l["line"].as_u64().unwrap())
+        .collect();
+    assert_eq!(lines, vec![2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn test_explain_shows_the_recorded_code_after_the_file_changed() {
+    let dir = repo();
+    review(dir.path());
+    fs::remove_file(dir.path().join("charge.py")).unwrap();
+
+    let output = revet(dir.path(), &["explain", "SQL-001"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("SQL Injection"), "{}", stdout);
+    assert!(stdout.contains("This Finding: charge.py:5"), "{}", stdout);
+    let marked = stdout.lines().find(|l| l.trim_start().starts_with('>'));
+    assert!(
+        marked.is_some_and(|l| l.contains("5 │     cursor.execute(\"SELECT * FROM users")),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("4 │ def charge(cursor, user_id):"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("Suggestion:"), "{}", stdout);
+    assert!(stdout.contains("Auto-fix:"), "{}", stdout);
+}
+
+#[test]
+fn test_unknown_id_lists_findings_of_the_same_prefix() {
+    let dir = repo();
+    review(dir.path());
+
+    let output = revet(dir.path(), &["explain", "SQL-007"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("SQL-007 is not in the last run"),
+        "{}",
+        stderr
+    );
+    let near: Vec<&str> = stderr
+        .lines()
+        .filter_map(|l| l.trim_start().strip_prefix("SQL-"))
+        .collect();
+    assert_eq!(near.len(), 2, "{}", stderr);
+    // Closest number first
+    assert!(near[0].starts_with("002 charge.py:6"), "{}", stderr);
+}
+
+#[test]
+fn test_explain_without_a_recorded_run_explains_the_category() {
+    let dir = repo();
+    let output = revet(dir.path(), &["explain", "SQL-001"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("SQL Injection"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No findings recorded yet"));
+}
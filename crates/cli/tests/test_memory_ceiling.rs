@@ -0,0 +1,149 @@
+//! `[performance] max_memory_mb`: a ceiling the review is always over
+//! sheds load in order at each stage, then stops with the findings so far
+//! and exits with the environment-error code
+
+use clap::Parser;
+use revet_cli::commands::review;
+use revet_cli::Cli;
+use revet_core::memory::ShedStep;
+use revet_core::{Shedding, StoragePaths, Timings};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+mod common;
+use common::git;
+
+const CONFIG: &str = "\
+[modules]
+dead_code = true
+refactor = true
+hotspots = true
+";
+
+fn cli(args: &[&str]) -> Cli {
+    let mut argv = vec!["revet"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+/// `main` with a clean `app.py`, changed in the working tree to add a
+/// hardcoded secret (content analyzer) and an unused function (graph
+/// analyzer). A previous run recorded `dead_code` as the hungriest module.
+fn repo(max_memory_mb: u64) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path();
+    fs::write(
+        path.join(".revet.toml"),
+        format!(
+            "{}\n[performance]\nmax_memory_mb = {}\n",
+            CONFIG, max_memory_mb
+        ),
+    )
+    .unwrap();
+    fs::write(path.join(".gitignore"), ".revet-cache/\n").unwrap();
+    fs::write(path.join("app.py"), "def main():\n    print(1)\n").unwrap();
+    git(path, &["init", "-q", "-b", "main"]);
+    git(path, &["add", "."]);
+    git(path, &["commit", "-q", "-m", "base"]);
+    fs::write(
+        path.join("app.py"),
+        "def orphan(x):\n    return x * 2\n\n\ndef main():\n    password = \"hunter2-Secret-Value\"\n    print(password)\n",
+    )
+    .unwrap();
+
+    let mut timings = Timings::default();
+    timings.peaks.insert("dead_code".to_string(), 512 << 20);
+    timings.peaks.insert("cycles".to_string(), 1 << 20);
+    timings.save(&StoragePaths::in_repo(path)).unwrap();
+    dir
+}
+
+fn has(findings: &[revet_core::Finding], prefix: &str) -> bool {
+    findings.iter().any(|f| f.id.starts_with(prefix))
+}
+
+/// (step, stage, applied) of each step taken
+fn steps(shed: &[ShedStep]) -> Vec<(Shedding, &str, bool)> {
+    shed.iter()
+        .map(|s| (s.step, s.stage.as_str(), s.applied))
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_load_is_shed_in_order_then_the_review_stops() {
+    let dir = repo(1);
+    let run = review::analyze(dir.path(), &cli(&["--diff", "main"])).unwrap();
+
+    let memory = run.summary.memory.as_ref().unwrap();
+    assert_eq!(memory.max_memory_mb, 1);
+    assert!(memory.peak_mb >= 1);
+    assert_eq!(
+        steps(&memory.shed),
+        [
+            (Shedding::SmallerBatches, "domain analyzers", true),
+            (Shedding::DropCaches, "code graph", true),
+            (Shedding::LazyGraph, "graph analyzers", false),
+            (Shedding::SkipAnalyzers, "graph analyzers", true),
+        ]
+    );
+    // Ranked by the recorded peak allocation
+    assert_eq!(memory.shed[3].detail, "dead code analyzer");
+    assert!(memory.exceeded);
+    assert_eq!(
+        memory.stopped_before.as_deref(),
+        Some("whole-repository scans")
+    );
+
+    // Content findings survive; the skipped analyzer and the scans after
+    // the stop report nothing
+    assert!(has(&run.findings, "SEC"));
+    assert!(!has(&run.findings, "DEAD"));
+    assert!(!has(&run.findings, "HOT"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_exceeded_ceiling_flushes_findings_and_exits_3() {
+    let dir = repo(1);
+    let output = Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(["--diff", "main", "--format", "json", "--print-exit-reason"])
+        .current_dir(dir.path())
+        .env_remove("REVET_LOCALE")
+        .env_remove("GITHUB_ACTIONS")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(3), "{}", stderr);
+    assert!(stderr.contains("memory: smaller analyzer batches before domain analyzers"));
+    assert!(stderr.contains("max_memory_mb = 1 before whole-repository scans"));
+    assert_eq!(
+        stderr.lines().last(),
+        Some("exit=3 reason=environment_error")
+    );
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let ids: Vec<&str> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["id"].as_str().unwrap())
+        .collect();
+    assert!(ids.iter().any(|id| id.starts_with("SEC")), "{:?}", ids);
+    let memory = &report["summary"]["memory"];
+    assert_eq!(memory["exceeded"], true);
+    assert_eq!(memory["shed"][0]["step"], "smaller_batches");
+    assert_eq!(memory["shed"][3]["step"], "skip_analyzers");
+}
+
+#[test]
+fn test_ceiling_with_room_to_spare() {
+    let dir = repo(1 << 20);
+    let run = review::analyze(dir.path(), &cli(&["--diff", "main"])).unwrap();
+    let memory = run.summary.memory.as_ref().unwrap();
+    assert!(memory.shed.is_empty());
+    assert!(!memory.exceeded);
+    assert!(has(&run.findings, "SEC") && has(&run.findings, "DEAD"));
+}
@@ -0,0 +1,90 @@
+//! Peak memory of `revet multi` must not grow with the number of repos:
+//! each worker drops its code graph before taking the next repository.
+//!
+//! Kept alone in this file because peak RSS is process-wide.
+
+#![cfg(target_os = "linux")]
+
+use clap::Parser;
+use revet_cli::commands::multi::analyze_workspace;
+use revet_cli::Cli;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+const FILES_PER_REPO: usize = 30;
+const FUNCTIONS_PER_FILE: usize = 30;
+
+/// Peak resident set size of this process, in kB
+fn peak_rss_kb() -> usize {
+    let status = fs::read_to_string("/proc/self/status").unwrap();
+    status
+        .lines()
+        .find_map(|l| l.strip_prefix("VmHWM:"))
+        .and_then(|v| v.trim().trim_end_matches("kB").trim().parse().ok())
+        .expect("VmHWM in /proc/self/status")
+}
+
+fn make_repo(dir: &Path) {
+    fs::create_dir_all(dir).unwrap();
+    for file in 0..FILES_PER_REPO {
+        let mut src = String::new();
+        for func in 0..FUNCTIONS_PER_FILE {
+            let _ = writeln!(
+                src,
+                "def handler_{file}_{func}(request, limit):\n    \
+                 total = helper_{file}_{prev}(request, limit)\n    \
+                 return total + len(request)\n",
+                prev = func.saturating_sub(1),
+            );
+        }
+        fs::write(dir.join(format!("module_{file}.py")), src).unwrap();
+    }
+    let ok = Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(dir)
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false);
+    assert!(ok, "git init failed");
+}
+
+fn run_workspace(root: &Path, repos: usize) -> usize {
+    let _ = fs::remove_dir_all(root);
+    for i in 0..repos {
+        make_repo(&root.join(format!("repos/r{i:02}")));
+    }
+    fs::write(
+        root.join("revet-workspace.toml"),
+        "repos = [\"repos/*\"]\njobs = 2\n",
+    )
+    .unwrap();
+
+    let cli = Cli::try_parse_from(["revet", "--full", "multi"]).unwrap();
+    let ws = analyze_workspace(Some(root), Some(2), &cli).unwrap();
+    assert_eq!(ws.outcomes.len(), repos);
+    for o in &ws.outcomes {
+        let review = o.result.as_ref().expect("repo reviewed");
+        assert_eq!(review.summary.files_analyzed, FILES_PER_REPO);
+    }
+    peak_rss_kb()
+}
+
+#[test]
+fn test_peak_rss_flat_as_repo_count_grows() {
+    let tmp = TempDir::new().unwrap();
+    let before = peak_rss_kb();
+    let small = run_workspace(&tmp.path().join("small"), 4);
+    let large = run_workspace(&tmp.path().join("large"), 16);
+
+    // Retaining graphs would add roughly three times the small run's growth
+    let small_growth = small.saturating_sub(before);
+    let large_growth = large.saturating_sub(small);
+    eprintln!("peak RSS: before {before} kB, 4 repos {small} kB, 16 repos {large} kB");
+    assert!(
+        large_growth <= small_growth / 2 + 8 * 1024,
+        "peak RSS grew by {large_growth} kB from 4 to 16 repos (4 repos added {small_growth} kB)"
+    );
+}
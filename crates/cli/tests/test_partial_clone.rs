@@ -0,0 +1,171 @@
+//! Partial clones and sparse checkouts: a blobless clone of a fixture
+//! repository, sparse to `app/`, degrades what needs the blobs it lacks
+//! instead of failing, fetches none of them under `[git] allow_fetch =
+//! false`, and leaves the paths outside the sparse checkout out
+
+use clap::Parser;
+use revet_cli::commands::review;
+use revet_cli::Cli;
+use revet_core::{Feature, SkipReason};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+
+mod common;
+use common::git;
+
+fn cli(args: &[&str]) -> Cli {
+    let mut argv = vec!["revet"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+/// Whether `spec` (`<rev>:<path>`) is in the clone. The `file://` remote
+/// is made unusable so that git cannot fetch it on demand.
+fn has_object(dir: &Path, spec: &str) -> bool {
+    Command::new("git")
+        .args(["-c", "protocol.file.allow=never"])
+        .args(["cat-file", "-e", spec])
+        .stderr(Stdio::null())
+        .current_dir(dir)
+        .status()
+        .unwrap()
+        .success()
+}
+
+/// Objects in the clone's packs, which a fetch adds to
+fn packed_objects(dir: &Path) -> String {
+    git(dir, &["count-objects", "-v"])
+        .lines()
+        .find(|line| line.starts_with("in-pack:"))
+        .unwrap()
+        .to_string()
+}
+
+/// An upstream with `app/` and `legacy/` edited over two commits, and a
+/// blobless clone of it checked out sparsely to `app/`, with `config` as
+/// its (untracked) `.revet.toml`
+fn fixture(config: &str) -> (TempDir, TempDir) {
+    let upstream = TempDir::new().unwrap();
+    let src = upstream.path();
+    fs::create_dir_all(src.join("app")).unwrap();
+    fs::create_dir_all(src.join("legacy")).unwrap();
+    fs::write(src.join("app/api.py"), "def handler():\n    return 1\n").unwrap();
+    fs::write(src.join("legacy/old.py"), "def old():\n    return 1\n").unwrap();
+    fs::write(src.join("setup.py"), "NAME = \"fixture\"\n").unwrap();
+    git(src, &["init", "-q", "-b", "main"]);
+    git(src, &["config", "uploadpack.allowfilter", "true"]);
+    git(src, &["config", "uploadpack.allowanysha1inwant", "true"]);
+    git(src, &["add", "."]);
+    git(src, &["commit", "-q", "-m", "Initial"]);
+    fs::write(
+        src.join("app/api.py"),
+        "def handler():\n    return 2\n\n\ndef other():\n    return 3\n",
+    )
+    .unwrap();
+    fs::write(src.join("legacy/old.py"), "def old():\n    return 2\n").unwrap();
+    git(src, &["commit", "-q", "-am", "Change both"]);
+
+    let clone = TempDir::new().unwrap();
+    let url = format!("file://{}", src.display());
+    let dst = clone.path().to_str().unwrap();
+    git(
+        src,
+        &[
+            "clone",
+            "-q",
+            "--filter=blob:none",
+            "--no-checkout",
+            &url,
+            dst,
+        ],
+    );
+    git(clone.path(), &["sparse-checkout", "set", "--cone", "app"]);
+    git(clone.path(), &["checkout", "-q", "main"]);
+    fs::write(clone.path().join(".revet.toml"), config).unwrap();
+    (upstream, clone)
+}
+
+#[test]
+fn test_no_fetch_degrades_changed_lines() {
+    let (_upstream, clone) = fixture("[git]\nallow_fetch = false\n");
+    let dir = clone.path();
+    assert!(!has_object(dir, "HEAD~1:app/api.py"));
+    let before = packed_objects(dir);
+
+    let run = review::analyze(dir, &cli(&["--diff", "HEAD~1", "--coverage-report"])).unwrap();
+    assert_eq!(run.summary.files_analyzed, 1);
+    let changed = run
+        .summary
+        .degraded
+        .iter()
+        .find(|d| d.feature == Feature::ChangedLines)
+        .expect("changed lines degraded");
+    assert_eq!(changed.skipped, 1);
+    assert!(changed.reason.contains("allow_fetch = false"), "{changed}");
+
+    // Nothing was fetched
+    assert_eq!(packed_objects(dir), before);
+    assert!(!has_object(dir, "HEAD~1:app/api.py"));
+}
+
+#[test]
+fn test_fetch_fills_in_missing_blobs() {
+    let (_upstream, clone) = fixture("");
+    let dir = clone.path();
+
+    let run = review::analyze(dir, &cli(&["--diff", "HEAD~1"])).unwrap();
+    assert!(
+        !run.summary
+            .degraded
+            .iter()
+            .any(|d| d.feature == Feature::ChangedLines),
+        "{:?}",
+        run.summary.degraded
+    );
+    assert!(has_object(dir, "HEAD~1:app/api.py"));
+    // Only what the diff needed, not what the sparse checkout leaves out
+    assert!(!has_object(dir, "HEAD~1:legacy/old.py"));
+}
+
+#[test]
+fn test_fetch_limit() {
+    let (_upstream, clone) = fixture("[git]\nfetch_limit = 0\n");
+    let run = review::analyze(clone.path(), &cli(&["--diff", "HEAD~1"])).unwrap();
+    let changed = run
+        .summary
+        .degraded
+        .iter()
+        .find(|d| d.feature == Feature::ChangedLines)
+        .expect("changed lines degraded");
+    assert!(changed.reason.contains("fetch_limit"), "{changed}");
+    assert!(!has_object(clone.path(), "HEAD~1:app/api.py"));
+}
+
+#[test]
+fn test_sparse_excluded_paths_skipped() {
+    let (_upstream, clone) = fixture("[git]\nallow_fetch = false\n");
+    let dir = clone.path();
+    // Left behind on disk, outside the sparse checkout
+    fs::create_dir_all(dir.join("legacy")).unwrap();
+    fs::write(dir.join("legacy/stray.py"), "def stray():\n    return 1\n").unwrap();
+
+    let run = review::analyze(dir, &cli(&["--full", "--coverage-report"])).unwrap();
+    let ledger = run.ledger.unwrap();
+    assert_eq!(
+        ledger.skip_reason(&dir.join("legacy/stray.py")),
+        Some(SkipReason::SparseExcluded)
+    );
+    assert_eq!(ledger.skip_reason(&dir.join("app/api.py")), None);
+    // Files at the top level are always in a cone-mode checkout
+    assert_eq!(ledger.skip_reason(&dir.join("setup.py")), None);
+
+    let run = review::analyze(
+        dir,
+        &cli(&["--full", "--coverage-report", "--include-sparse-excluded"]),
+    )
+    .unwrap();
+    let ledger = run.ledger.unwrap();
+    assert_eq!(ledger.skip_reason(&dir.join("legacy/stray.py")), None);
+}
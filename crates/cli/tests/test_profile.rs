@@ -0,0 +1,186 @@
+use clap::Parser;
+use revet_cli::profile::resolve;
+use revet_cli::Cli;
+use revet_core::config::RuleSelection;
+use revet_core::{CodeKind, ProfileConfig, RevetConfig, Scope};
+
+fn cli(args: &[&str]) -> Cli {
+    let mut argv = vec!["revet"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+#[test]
+fn test_profile_flag_selects_profile() {
+    let mut config = RevetConfig::default();
+    let settings = resolve(&cli(&["--profile", "quick"]), &mut config).unwrap();
+
+    assert_eq!(settings.name, "quick");
+    assert_eq!(settings.scope, Scope::Changed);
+    assert!(!settings.graph);
+    assert!(settings.overrides.is_empty());
+    assert!(!config.modules.duplication);
+}
+
+#[test]
+fn test_flags_win_over_profile_and_are_recorded() {
+    let mut config = RevetConfig::default();
+    let settings = resolve(
+        &cli(&[
+            "--profile",
+            "quick",
+            "--full",
+            "--ai",
+            "--fail-on",
+            "warning",
+            "--diff",
+            "develop",
+        ]),
+        &mut config,
+    )
+    .unwrap();
+
+    assert_eq!(settings.scope, Scope::Full);
+    assert!(settings.ai);
+    assert_eq!(settings.fail_on, "warning");
+    assert_eq!(settings.diff_base, "develop");
+    assert!(!settings.graph); // untouched by flags
+    assert_eq!(
+        settings.overrides,
+        vec!["--full", "--diff develop", "--ai", "--fail-on warning"]
+    );
+    assert_eq!(config.general.fail_on, "warning");
+    assert_eq!(config.general.diff_base, "develop");
+
+    let repro = settings.reproducibility();
+    assert_eq!(repro.profile, "quick");
+    assert_eq!(repro.overrides.len(), 4);
+}
+
+#[test]
+fn test_module_flag_enables_only_listed_modules() {
+    let mut config = RevetConfig::default();
+    let settings = resolve(&cli(&["--module", "security,ml"]), &mut config).unwrap();
+
+    assert!(config.modules.security);
+    assert!(config.modules.ml);
+    assert!(!config.modules.complexity);
+    assert!(!config.modules.duplication);
+    assert_eq!(settings.overrides, vec!["--module security,ml"]);
+}
+
+#[test]
+fn test_module_flag_rejects_unknown_module() {
+    let mut config = RevetConfig::default();
+    let err = resolve(&cli(&["--module", "securty"]), &mut config).unwrap_err();
+    assert!(err.to_string().contains("unknown module \"securty\""));
+}
+
+#[test]
+fn test_module_flag_selects_sub_rules() {
+    let mut config = RevetConfig::default();
+    resolve(
+        &cli(&[
+            "--module",
+            "error_handling:swallowed,async_patterns:unawaited,security",
+        ]),
+        &mut config,
+    )
+    .unwrap();
+
+    assert!(config.modules.error_handling);
+    assert!(config.modules.async_patterns);
+    assert!(config.modules.security);
+    assert!(!config.modules.cycles);
+    assert_eq!(
+        config.rule_selection("error_handling").only,
+        vec!["swallowed"]
+    );
+    assert_eq!(
+        config.rule_selection("async_patterns").only,
+        vec!["unawaited"]
+    );
+    assert!(config.rule_selection("security").only.is_empty());
+}
+
+#[test]
+fn test_module_flag_whole_module_wins_over_sub_rule() {
+    let mut config = RevetConfig::default();
+    resolve(
+        &cli(&["--module", "error_handling:unwrap,error_handling"]),
+        &mut config,
+    )
+    .unwrap();
+    assert!(config.rule_selection("error_handling").allows("swallowed"));
+}
+
+#[test]
+fn test_module_flag_rejects_unknown_sub_rule() {
+    let mut config = RevetConfig::default();
+    let err = resolve(&cli(&["--module", "error_handling:swalowed"]), &mut config)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("unknown sub-rule \"swalowed\" for error_handling"));
+    assert!(err.contains("swallowed"), "lists valid sub-rules: {err}");
+}
+
+#[test]
+fn test_configured_unknown_sub_rule_fails_at_startup() {
+    let mut config = RevetConfig::default();
+    config.analyzers.rules.insert(
+        "error_handling".to_string(),
+        RuleSelection {
+            only: Vec::new(),
+            disable: vec!["unwarp".to_string()],
+            ..Default::default()
+        },
+    );
+    let err = resolve(&cli(&[]), &mut config).unwrap_err().to_string();
+    assert!(err.starts_with("[analyzers.error_handling] unknown sub-rule \"unwarp\""));
+    assert!(err.contains("unwrap"));
+}
+
+#[test]
+fn test_only_flag_restricts_code_kind() {
+    let mut config = RevetConfig::default();
+    let settings = resolve(&cli(&[]), &mut config).unwrap();
+    assert_eq!(settings.only, None);
+
+    let settings = resolve(&cli(&["--only", "production"]), &mut config).unwrap();
+    assert_eq!(settings.only, Some(CodeKind::Production));
+    assert_eq!(settings.overrides, vec!["--only production"]);
+    assert!(Cli::try_parse_from(["revet", "--only", "tests"]).is_err());
+}
+
+#[test]
+fn test_unknown_profile_is_an_error() {
+    let mut config = RevetConfig::default();
+    let err = resolve(&cli(&["--profile", "turbo"]), &mut config).unwrap_err();
+    assert!(err.to_string().contains("unknown profile"));
+}
+
+#[test]
+fn test_configured_profile_via_flag() {
+    let mut config = RevetConfig::default();
+    config.profiles.insert(
+        "nightly".to_string(),
+        ProfileConfig {
+            extends: Some("deep".to_string()),
+            ai: Some(true),
+            ..Default::default()
+        },
+    );
+    let settings = resolve(&cli(&["--profile", "nightly"]), &mut config).unwrap();
+
+    assert_eq!(settings.scope, Scope::Full);
+    assert!(settings.ai);
+    assert!(settings.history_secrets);
+    assert!(config.modules.duplication);
+}
+
+#[test]
+fn test_no_redact_still_applies() {
+    let mut config = RevetConfig::default();
+    resolve(&cli(&["--no-redact", "--confirm-no-redact"]), &mut config).unwrap();
+    assert!(!config.output.redact_secrets);
+}
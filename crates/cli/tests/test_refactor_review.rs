@@ -0,0 +1,85 @@
+use clap::Parser;
+use revet_cli::commands::review;
+use revet_cli::Cli;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod common;
+use common::git;
+
+fn cli(args: &[&str]) -> Cli {
+    let mut argv = vec!["revet"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+fn copy_tree(from: &Path, to: &Path) {
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            fs::create_dir_all(&target).unwrap();
+            copy_tree(&entry.path(), &target);
+        } else {
+            fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+/// The `refactor_verification` fixture as a repository: `base/` committed,
+/// `head/` in the working tree, with `refactor` set in `[modules]`
+fn fixture_repo(refactor: bool) -> TempDir {
+    let src =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/refactor_verification");
+    let dir = TempDir::new().unwrap();
+    let config = fs::read_to_string(src.join(".revet.toml"))
+        .unwrap()
+        .replace("refactor = true", &format!("refactor = {}", refactor));
+    fs::write(dir.path().join(".revet.toml"), config).unwrap();
+    copy_tree(&src.join("base"), dir.path());
+    git(dir.path(), &["init", "-q"]);
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-q", "-m", "base"]);
+    copy_tree(&src.join("head"), dir.path());
+    dir
+}
+
+fn refactor_findings(run: &review::ReviewRun) -> Vec<(String, usize)> {
+    let mut rows: Vec<_> = run
+        .findings
+        .iter()
+        .filter(|f| f.id.starts_with("REF-"))
+        .map(|f| (f.rule.clone().unwrap_or_default(), f.line))
+        .collect();
+    rows.sort();
+    rows
+}
+
+#[test]
+fn test_diff_review_reports_callers_outside_the_diff() {
+    let dir = fixture_repo(true);
+    let run = review::analyze(dir.path(), &cli(&["--diff", "HEAD"])).unwrap();
+    assert_eq!(
+        refactor_findings(&run),
+        vec![
+            ("refactor:arity_mismatch".to_string(), 4),
+            ("refactor:arity_mismatch".to_string(), 5),
+            ("refactor:stale_reference".to_string(), 1),
+        ]
+    );
+}
+
+#[test]
+fn test_full_review_skips_refactor_verification() {
+    let dir = fixture_repo(true);
+    let run = review::analyze(dir.path(), &cli(&["--full"])).unwrap();
+    assert!(refactor_findings(&run).is_empty());
+}
+
+#[test]
+fn test_module_is_off_by_default() {
+    let dir = fixture_repo(false);
+    let run = review::analyze(dir.path(), &cli(&["--diff", "HEAD"])).unwrap();
+    assert!(refactor_findings(&run).is_empty());
+}
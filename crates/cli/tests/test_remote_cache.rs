@@ -0,0 +1,280 @@
+//! `[cache.remote]` against an in-process HTTP stub: a second job reuses
+//! what the first uploaded, errors fall back to the local cache, and
+//! clients racing on the same keys never see a wrong blob
+
+use revet_cli::remote_cache;
+use revet_core::config::RemoteCacheConfig;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+
+const SQL_INJECTION: &str =
+    "def charge(cursor, user_id):\n    cursor.execute(\"SELECT * FROM users WHERE id = \" + user_id)\n";
+
+/// A cache server keeping blobs in memory
+struct Stub {
+    url: String,
+    blobs: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+}
+
+/// Start a stub; with `token`, requests without it are refused.
+/// Each connection gets its own thread, so clients keeping several
+/// connections alive at once are never queued behind each other
+fn stub(token: Option<&'static str>) -> Stub {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/revet", listener.local_addr().unwrap());
+    let blobs: Arc<Mutex<HashMap<String, Vec<u8>>>> = Arc::default();
+    let shared = Arc::clone(&blobs);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let shared = Arc::clone(&shared);
+            std::thread::spawn(move || {
+                let _ = serve(stream?, &shared, token);
+                std::io::Result::Ok(())
+            });
+        }
+    });
+    Stub { url, blobs }
+}
+
+/// Answer the requests of one kept-alive connection until the client closes it
+fn serve(
+    stream: TcpStream,
+    blobs: &Mutex<HashMap<String, Vec<u8>>>,
+    token: Option<&str>,
+) -> std::io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let mut parts = line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let key = parts
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches("/revet/")
+            .to_string();
+
+        let mut length = 0;
+        let mut authorized = token.is_none();
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header)?;
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            let (name, value) = header.split_once(':').unwrap_or((header, ""));
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = value.parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("Authorization") {
+                authorized |= token.is_some_and(|t| value == format!("Bearer {}", t));
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+
+        let (status, reply) = if !authorized {
+            (401, Vec::new())
+        } else if method == "PUT" {
+            blobs.lock().unwrap().insert(key, body);
+            (200, Vec::new())
+        } else {
+            match blobs.lock().unwrap().get(&key) {
+                Some(blob) => (200, blob.clone()),
+                None => (404, Vec::new()),
+            }
+        };
+        write!(
+            writer,
+            "HTTP/1.1 {} -\r\nContent-Length: {}\r\n\r\n",
+            status,
+            reply.len()
+        )?;
+        writer.write_all(&reply)?;
+    }
+}
+
+fn repo(url: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join(".revet.toml"),
+        format!("[cache.remote]\nurl = {:?}\n", url),
+    )
+    .unwrap();
+    fs::write(dir.path().join("charge.py"), SQL_INJECTION).unwrap();
+    fs::write(
+        dir.path().join("refund.py"),
+        SQL_INJECTION.replace("charge", "refund"),
+    )
+    .unwrap();
+    dir
+}
+
+/// A review as a fresh CI job: no local cache
+fn job(dir: &Path, token: Option<&str>) -> (Value, String) {
+    let _ = fs::remove_dir_all(dir.join(".revet-cache"));
+    let mut command = Command::new(env!("CARGO_BIN_EXE_revet"));
+    command
+        .args(["--full", "--format", "json", "--timings", "--no-baseline"])
+        .current_dir(dir)
+        .env_remove("REVET_DATA_DIR")
+        .env_remove("REVET_CACHE_TOKEN")
+        .env("NO_COLOR", "1");
+    if let Some(token) = token {
+        command.env("REVET_CACHE_TOKEN", token);
+    }
+    let output = command.output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let json =
+        serde_json::from_slice(&output.stdout).unwrap_or_else(|e| panic!("{}\n{}", e, stderr));
+    (json, stderr)
+}
+
+fn ids(json: &Value) -> Vec<String> {
+    let mut ids: Vec<String> = json["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| format!("{} {}", f["file"], f["line"]))
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn test_second_job_reuses_the_first() {
+    let server = stub(None);
+    let dir = repo(&server.url);
+
+    let (first, _) = job(dir.path(), None);
+    let stats = &first["summary"]["timings"]["remote_cache"];
+    assert_eq!(stats["hits"], 0);
+    assert!(stats["uploads"].as_u64().unwrap() >= 3, "{}", stats);
+    assert_eq!(stats["errors"], 0);
+    let keys: Vec<String> = server.blobs.lock().unwrap().keys().cloned().collect();
+    assert!(keys.iter().any(|k| k.contains("/analysis/")));
+    assert!(keys.iter().any(|k| k.contains("/graph/")));
+    assert!(keys.iter().all(|k| k.starts_with(revet_core::VERSION)));
+
+    let (second, stderr) = job(dir.path(), None);
+    assert!(stderr.contains("(cached)"), "{}", stderr);
+    let stats = &second["summary"]["timings"]["remote_cache"];
+    assert!(stats["hits"].as_u64().unwrap() >= 3, "{}", stats);
+    assert_eq!(stats["uploads"], 0);
+    assert_eq!(ids(&second), ids(&first));
+    assert_eq!(ids(&second).len(), 2);
+}
+
+#[test]
+fn test_checkout_directory_does_not_matter() {
+    // Per-executor workspaces: the same commit checked out in two places
+    let server = stub(None);
+    let first_dir = repo(&server.url);
+    let second_dir = repo(&server.url);
+
+    let (first, _) = job(first_dir.path(), None);
+    let (second, _) = job(second_dir.path(), None);
+    let stats = &second["summary"]["timings"]["remote_cache"];
+    assert!(stats["hits"].as_u64().unwrap() >= 3, "{}", stats);
+    assert_eq!(stats["uploads"], 0);
+    // Findings replayed from the first checkout point into the second
+    let moved: Vec<String> = ids(&first)
+        .iter()
+        .map(|id| {
+            id.replace(
+                &first_dir.path().display().to_string(),
+                &second_dir.path().display().to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(ids(&second), moved);
+}
+
+#[test]
+fn test_errors_fall_back_to_local_only() {
+    // Nothing listens on port 1
+    let dir = repo("http://127.0.0.1:1/revet");
+    let (json, stderr) = job(dir.path(), None);
+    assert_eq!(ids(&json).len(), 2);
+    assert_eq!(
+        stderr
+            .matches("remote cache http://127.0.0.1:1/revet unavailable")
+            .count(),
+        1,
+        "{}",
+        stderr
+    );
+    assert_eq!(json["summary"]["timings"]["remote_cache"]["errors"], 1);
+    assert!(stderr.contains("local only since the first"), "{}", stderr);
+}
+
+#[test]
+fn test_bearer_token_from_the_environment() {
+    let server = stub(Some("s3cret"));
+    let dir = repo(&server.url);
+
+    let (json, stderr) = job(dir.path(), None);
+    assert!(stderr.contains("401"), "{}", stderr);
+    assert_eq!(json["summary"]["timings"]["remote_cache"]["errors"], 1);
+    assert!(server.blobs.lock().unwrap().is_empty());
+
+    let (json, _) = job(dir.path(), Some("s3cret"));
+    assert_eq!(json["summary"]["timings"]["remote_cache"]["errors"], 0);
+    assert!(!server.blobs.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_clients_racing_on_the_same_keys() {
+    let server = stub(None);
+    // Generous timeout: a loaded test machine must not turn slowness into errors
+    let config = RemoteCacheConfig {
+        timeout_ms: 60_000,
+        ..RemoteCacheConfig::default()
+    };
+    let blob = |i: usize| format!("blob {} ", i).repeat(200).into_bytes();
+
+    // Two clients, each on several threads, write and read the same keys
+    std::thread::scope(|scope| {
+        for _ in 0..2 {
+            let store = Arc::new(remote_cache::store(&server.url, &config).unwrap());
+            for thread in 0..4 {
+                let store = Arc::clone(&store);
+                scope.spawn(move || {
+                    for round in 0..25 {
+                        let i = (thread + round) % 10;
+                        let key = format!("race/{}", i);
+                        match store.get(&key).unwrap() {
+                            Some(bytes) => assert_eq!(bytes, blob(i)),
+                            None => store.put(&key, &blob(i)).unwrap(),
+                        }
+                    }
+                });
+            }
+        }
+    });
+
+    let blobs = server.blobs.lock().unwrap();
+    assert_eq!(blobs.len(), 10);
+    for i in 0..10 {
+        assert_eq!(blobs[&format!("race/{}", i)], blob(i));
+    }
+}
+
+#[cfg(not(feature = "s3-cache"))]
+#[test]
+fn test_s3_needs_the_feature() {
+    let result = remote_cache::store("s3://bucket/revet", &RemoteCacheConfig::default());
+    let error = result.err().unwrap().to_string();
+    assert!(error.contains("s3-cache"), "{}", error);
+}
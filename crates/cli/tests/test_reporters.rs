@@ -0,0 +1,150 @@
+//! Several `--format FORMAT[=PATH]` reports from one review run: each the
+//! same as a run of its own, and destinations that clash are usage errors
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+const SQL_INJECTION: &str =
+    "def charge(cursor, user_id):\n    cursor.execute(\"SELECT * FROM users WHERE id = \" + user_id)\n";
+
+fn repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for name in ["charge", "refund"] {
+        fs::write(
+            dir.path().join(format!("{}.py", name)),
+            SQL_INJECTION.replace("charge", name),
+        )
+        .unwrap();
+    }
+    dir
+}
+
+/// Review `dir` from scratch: without the cache of an earlier run, which
+/// would make this one a diff against it
+fn revet(dir: &Path, args: &[&str]) -> Output {
+    let _ = fs::remove_dir_all(dir.join(".revet-cache"));
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(["--full", "--no-baseline"])
+        .args(args)
+        .arg("review")
+        .arg(dir)
+        .env_remove("REVET_LOCALE")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+/// Terminal output without the lines that differ between runs
+fn stable(stdout: &[u8]) -> String {
+    String::from_utf8_lossy(stdout)
+        .lines()
+        .filter(|l| !l.contains("Time:") && !l.contains("Run log:"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_one_run_writes_what_separate_runs_write() {
+    let repo = repo();
+    let out = TempDir::new().unwrap();
+    let file = |name: &str| out.path().join(name).to_string_lossy().into_owned();
+
+    let combined = revet(
+        repo.path(),
+        &[
+            "--format",
+            &format!("sarif={}", file("one.sarif")),
+            "--format",
+            &format!("json={}", file("one.json")),
+            "--format",
+            &format!("markdown={}", file("one.md")),
+            "--format",
+            "terminal",
+        ],
+    );
+    assert_eq!(combined.status.code(), Some(1), "{:?}", combined);
+
+    for (format, name) in [
+        ("sarif", "revet.sarif"),
+        ("json", "revet.json"),
+        ("markdown", "revet.md"),
+    ] {
+        let single = revet(
+            repo.path(),
+            &[
+                "--format",
+                format,
+                "--output",
+                &file(&format!("separate-{}", name)),
+            ],
+        );
+        assert_eq!(single.status.code(), Some(1), "{}: {:?}", format, single);
+        let extension = name.rsplit('.').next().unwrap();
+        assert_eq!(
+            fs::read(file(&format!("one.{}", extension))).unwrap(),
+            fs::read(file(&format!("separate-{}", name))).unwrap(),
+            "--format {}",
+            format
+        );
+    }
+
+    let terminal = revet(repo.path(), &["--format", "terminal"]);
+    assert_eq!(stable(&combined.stdout), stable(&terminal.stdout));
+    assert!(stable(&terminal.stdout).contains("refund.py"));
+
+    let markdown = fs::read_to_string(file("one.md")).unwrap();
+    assert!(
+        markdown.starts_with("## revet review\n\n**Failed**"),
+        "{}",
+        markdown
+    );
+}
+
+#[test]
+fn test_markdown_prints_when_it_is_the_only_format() {
+    let repo = repo();
+    let output = revet(repo.path(), &["--format", "markdown"]);
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("## revet review"), "{}", stdout);
+    assert!(stdout.contains("| `charge.py` |"), "{}", stdout);
+}
+
+#[test]
+fn test_clashing_destinations_are_usage_errors() {
+    let repo = repo();
+    let out = TempDir::new().unwrap();
+    let report = out.path().join("report").to_string_lossy().into_owned();
+    let terminal_file = format!("terminal={}", report);
+    let json_file = format!("json={}", report);
+    let sarif_file = format!("sarif={}", report);
+    let cases: [(&[&str], &str); 6] = [
+        (
+            &["--format", "terminal", "--format", "json"],
+            "both print to stdout",
+        ),
+        (
+            &["--format", &terminal_file],
+            "--format terminal prints to stdout",
+        ),
+        (
+            &["--format", &json_file, "--format", &sarif_file],
+            "destination of two --format reports",
+        ),
+        (
+            &["--format", "json", "--format", "sarif", "--output", &report],
+            "--output is the file of a single --format",
+        ),
+        (&["--format", "json="], "names no file"),
+        (&["--format", "yaml"], "unknown format \"yaml\""),
+    ];
+    for (args, error) in cases {
+        let output = revet(repo.path(), args);
+        assert_eq!(output.status.code(), Some(2), "{:?}: {:?}", args, output);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{:?}: {}", args, stderr);
+    }
+    assert!(!out.path().join("report").exists());
+}
@@ -0,0 +1,149 @@
+//! Review effort estimate and suggested reviewers over the `review_estimate`
+//! fixture: a two-project diff whose band and factors are known by hand
+//! from the fixture's round weights
+
+use clap::Parser;
+use revet_cli::commands::review;
+use revet_cli::Cli;
+use revet_core::diff::Band;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn cli(args: &[&str]) -> Cli {
+    let mut argv = vec!["revet"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+/// Run git as `name`
+fn git_as(dir: &Path, name: &str, args: &[&str]) {
+    let user = format!("user.name={}", name);
+    let email = format!("user.email={}@example.com", name);
+    assert!(Command::new("git")
+        .args(["-c", &user, "-c", &email])
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap()
+        .success());
+}
+
+fn copy_tree(from: &Path, to: &Path) {
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            fs::create_dir_all(&target).unwrap();
+            copy_tree(&entry.path(), &target);
+        } else {
+            fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+/// The fixture as a repository: `api/` written by alice, `worker/` by bob,
+/// then `head/` committed by dev, the PR author
+fn fixture_repo() -> TempDir {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/review_estimate");
+    let dir = TempDir::new().unwrap();
+    fs::copy(src.join(".revet.toml"), dir.path().join(".revet.toml")).unwrap();
+    fs::copy(src.join("CODEOWNERS"), dir.path().join("CODEOWNERS")).unwrap();
+    copy_tree(&src.join("base"), dir.path());
+    git_as(dir.path(), "alice", &["init", "-q"]);
+    git_as(
+        dir.path(),
+        "alice",
+        &["add", ".revet.toml", "CODEOWNERS", "api"],
+    );
+    git_as(dir.path(), "alice", &["commit", "-q", "-m", "api"]);
+    git_as(dir.path(), "bob", &["add", "worker"]);
+    git_as(dir.path(), "bob", &["commit", "-q", "-m", "worker"]);
+    copy_tree(&src.join("head"), dir.path());
+    git_as(dir.path(), "dev", &["commit", "-q", "-a", "-m", "pricing"]);
+    dir
+}
+
+fn revet(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .args(args)
+        .current_dir(dir)
+        .env_remove("REVET_LOCALE")
+        .env_remove("REVET_DATA_DIR")
+        .env_remove("GITHUB_ACTIONS")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_estimate_of_the_fixture_diff() {
+    let dir = fixture_repo();
+    let run = review::analyze(dir.path(), &cli(&["--diff", "HEAD~1"])).unwrap();
+    let estimate = run.review_estimate.expect("diff runs carry an estimate");
+
+    // Six changed lines: three outside functions, two in `total`
+    // (complexity 6, so 1 + 0.5 × 5 each) and one in `retry_delay` (1)
+    let factors: Vec<(&str, f64, f64)> = estimate
+        .factors
+        .iter()
+        .filter(|f| f.weight > 0.0)
+        .map(|f| (f.name.as_str(), f.value, f.minutes))
+        .collect();
+    assert_eq!(
+        factors,
+        [
+            ("lines", 11.0, 11.0),
+            ("files", 2.0, 4.0),
+            ("projects", 2.0, 10.0),
+        ]
+    );
+    assert_eq!(estimate.changed_lines, 6);
+    assert_eq!(estimate.minutes, 25.0);
+    assert_eq!(estimate.band, Band::L);
+
+    // dev owns `api/` too but wrote the change; `worker/` is a team's
+    let reviewers: Vec<(&str, bool)> = estimate
+        .reviewers
+        .iter()
+        .map(|r| (r.author.as_str(), r.owner))
+        .collect();
+    assert_eq!(reviewers, [("alice@example.com", true)]);
+}
+
+#[test]
+fn test_estimate_lands_in_markdown_and_json_only() {
+    let dir = fixture_repo();
+    let args = ["--diff", "HEAD~1", "--fail-on", "never", "--format"];
+
+    let markdown = revet(dir.path(), &[&args[..], &["markdown"]].concat());
+    let markdown = String::from_utf8_lossy(&markdown.stdout);
+    assert!(
+        markdown.contains("**Review estimate: L** (~25 min, 6 changed lines)"),
+        "{}",
+        markdown
+    );
+    assert!(markdown.contains("`alice@example.com`"), "{}", markdown);
+    assert!(
+        markdown.contains("| projects | 2 | 5 | 10 |"),
+        "{}",
+        markdown
+    );
+
+    let json = revet(dir.path(), &[&args[..], &["json"]].concat());
+    let json: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+    assert_eq!(json["review_estimate"]["band"], "L");
+    assert_eq!(json["review_estimate"]["bands"][0], 10.0);
+
+    let terminal = revet(dir.path(), &[&args[..], &["terminal"]].concat());
+    let terminal = String::from_utf8_lossy(&terminal.stdout);
+    assert!(!terminal.contains("Review estimate"), "{}", terminal);
+}
+
+#[test]
+fn test_full_runs_have_no_estimate() {
+    let dir = fixture_repo();
+    let run = review::analyze(dir.path(), &cli(&["--full"])).unwrap();
+    assert!(run.review_estimate.is_none());
+}
@@ -0,0 +1,68 @@
+use revet_cli::output::github_comment::format_risk_comment;
+use revet_core::diff::risk::{score, FileRisk, SymbolRisk};
+use revet_core::{ChangeClassification, NodeKind, RiskLevel, RiskReport};
+use std::path::PathBuf;
+
+fn symbol(name: &str, file: &str, fan_in: usize, reach: f64) -> SymbolRisk {
+    let breakdown = score(fan_in, reach, false);
+    SymbolRisk {
+        name: name.to_string(),
+        kind: NodeKind::Function,
+        file: PathBuf::from(file),
+        line: 12,
+        classification: ChangeClassification::Breaking,
+        direct_fan_in: fan_in,
+        transitive_dependents: reach as usize,
+        decayed_reach: reach,
+        critical_dependents: 0,
+        score: breakdown.total,
+        level: if breakdown.total >= 60 {
+            RiskLevel::High
+        } else {
+            RiskLevel::Low
+        },
+        breakdown,
+    }
+}
+
+fn report(symbols: Vec<SymbolRisk>) -> RiskReport {
+    let top = &symbols[0];
+    RiskReport {
+        score: top.score,
+        level: top.level,
+        files: vec![FileRisk {
+            file: top.file.clone(),
+            score: top.score,
+            level: top.level,
+            symbols: 1,
+            top_symbol: top.display_name(),
+        }],
+        symbols,
+    }
+}
+
+#[test]
+fn risk_comment_has_headline_and_table() {
+    let body = format_risk_comment(&report(vec![
+        symbol("parse_order", "src/orders.py", 214, 300.0),
+        symbol("fmt_date", "src/util.py", 2, 2.0),
+    ]));
+    let lines: Vec<&str> = body.lines().collect();
+    assert_eq!(
+        lines[0],
+        "🔴 **revet** — Risk: 78 (high) — `parse_order()` has 214 direct callers"
+    );
+    assert!(body.contains("| Symbol | File | Score | Direct fan-in | Transitive | Critical |"));
+    assert!(body.contains("| `parse_order()` | `src/orders.py:12` | 78 (high) | 214 | 300 | 0 |"));
+    assert!(body.contains("| `fmt_date()` | `src/util.py:12` | 17 (low) | 2 | 2 | 0 |"));
+    assert!(body.trim_end().ends_with("<!-- revet:risk -->"));
+}
+
+#[test]
+fn risk_comment_truncates_long_tables() {
+    let symbols = (0..12)
+        .map(|i| symbol(&format!("f{}", i), "src/a.py", 1, 1.0))
+        .collect();
+    let body = format_risk_comment(&report(symbols));
+    assert!(body.contains("_2 more changed symbol(s) not shown._"));
+}
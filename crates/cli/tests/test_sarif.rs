@@ -1,5 +1,5 @@
-use revet_cli::output::sarif::build_sarif_log;
-use revet_core::{Finding, Severity};
+use revet_cli::output::sarif::{build_localized_sarif_log, build_sarif_log};
+use revet_core::{Finding, Locale, Msg, Severity};
 use std::path::{Path, PathBuf};
 
 fn make_finding(id: &str, severity: Severity, message: &str, file: &str, line: usize) -> Finding {
@@ -161,25 +161,39 @@ fn test_roundtrip_serialization() {
 }
 
 #[test]
-fn test_empty_file_path_produces_no_location() {
-    // Findings with no associated file (e.g. repo-level graph findings) must
-    // not emit an empty artifactLocation.uri, which is invalid SARIF and
-    // causes "locationFromSarifResult: expected artifact location" in consumers.
-    let findings = vec![Finding {
-        id: "IMPACT-001".to_string(),
-        severity: Severity::Warning,
-        message: "repo-level finding".to_string(),
-        file: PathBuf::new(), // empty — no file associated
-        line: 0,
-        affected_dependents: 3,
-        ..Default::default()
-    }];
+fn test_repository_level_finding_targets_the_repo() {
+    // A finding with no file must not emit an empty artifactLocation.uri,
+    // which is invalid SARIF ("locationFromSarifResult: expected artifact
+    // location"); it has no location and the repository as its target.
+    let findings = vec![
+        Finding {
+            id: "REPO-001".to_string(),
+            severity: Severity::Warning,
+            message: "No LICENSE file at the repository root".to_string(),
+            file: PathBuf::new(),
+            line: 0,
+            ..Default::default()
+        },
+        make_finding("SEC-001", Severity::Error, "located", "/repo/src/a.py", 3),
+    ];
     let log = build_sarif_log(&findings, Path::new("/repo"));
+    let results = &log.runs[0].results;
+    assert_eq!(results.len(), 2);
 
-    assert!(
-        log.runs[0].results.is_empty(),
-        "empty file path must produce no SARIF result (skipped entirely to avoid invalid empty URI)"
+    assert_eq!(results[0].rule_id, "REPO");
+    assert!(results[0].locations.is_empty());
+    let target = results[0].analysis_target.as_ref().unwrap();
+    assert_eq!(
+        (target.uri.as_str(), target.uri_base_id.as_str()),
+        (".", "%SRCROOT%")
     );
+    assert!(results[1].analysis_target.is_none());
+    assert_eq!(results[1].locations.len(), 1);
+
+    let json = serde_json::to_value(&log).unwrap();
+    let result = &json["runs"][0]["results"][0];
+    assert!(result.get("locations").is_none());
+    assert_eq!(result["analysisTarget"]["uri"], ".");
 }
 
 #[test]
@@ -199,3 +213,62 @@ fn test_unknown_prefix() {
         "Code review finding"
     );
 }
+
+#[test]
+fn test_column_range() {
+    let mut finding = make_finding("LOG-001", Severity::Warning, "pii", "/repo/app.ts", 3);
+    finding.column = Some(20);
+    finding.end_column = Some(33);
+    let log = build_sarif_log(
+        &[
+            finding,
+            make_finding("LOG-002", Severity::Warning, "pii", "/repo/b.ts", 1),
+        ],
+        Path::new("/repo"),
+    );
+
+    let region = &log.runs[0].results[0].locations[0].physical_location.region;
+    assert_eq!(region.start_column, Some(20));
+    assert_eq!(region.end_column, Some(33));
+
+    let json = serde_json::to_string(&log).unwrap();
+    assert!(json.contains("\"startColumn\":20"));
+    assert_eq!(json.matches("startColumn").count(), 1);
+}
+
+#[test]
+fn test_localized_message_keeps_english_text() {
+    let mut finding = make_finding("ERR-001", Severity::Warning, "", "/repo/lib.rs", 7);
+    let message = Msg::new("error-handling-unwrap");
+    finding.message = message.english();
+    finding.message_ref = Some(message);
+    let findings = vec![
+        finding,
+        make_finding("CUSTOM-001", Severity::Info, "custom", "/repo/a.py", 1),
+    ];
+
+    let log = build_localized_sarif_log(&findings, Path::new("/repo"), Locale::Ja);
+    let results = &log.runs[0].results;
+    assert_eq!(results[0].message.text, ".unwrap() call");
+    let props = results[0].properties.as_ref().unwrap();
+    assert_eq!(
+        props.localized_message.as_deref(),
+        Some(".unwrap() の呼び出し")
+    );
+    // Uncatalogued messages fall back to English
+    assert_eq!(
+        results[1]
+            .properties
+            .as_ref()
+            .unwrap()
+            .localized_message
+            .as_deref(),
+        Some("custom")
+    );
+    let json = serde_json::to_string(&log).unwrap();
+    assert!(json.contains("\"localized_message\""));
+
+    // English runs add nothing
+    let log = build_sarif_log(&findings, Path::new("/repo"));
+    assert!(log.runs[0].results[0].properties.is_none());
+}
@@ -0,0 +1,239 @@
+//! `revet ignore`: entries kept outside the repository, applied after the
+//! baseline, counted in the summary and skipped in CI
+
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// Variables any of which marks a CI environment
+const CI_VARS: &[&str] = &[
+    "CI",
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "BUILDKITE",
+    "CIRCLECI",
+    "JENKINS_URL",
+    "TF_BUILD",
+    "TEAMCITY_VERSION",
+];
+
+fn copy_tree(from: &Path, to: &Path) {
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            fs::create_dir_all(&target).unwrap();
+            copy_tree(&entry.path(), &target);
+        } else {
+            fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+/// The `api_dashboard` fixture as a git repository, with its own state
+/// home so entries never leak between tests
+struct Repo {
+    dir: TempDir,
+    state: TempDir,
+}
+
+impl Repo {
+    fn new() -> Self {
+        let dir = TempDir::new().unwrap();
+        copy_tree(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/api_dashboard"),
+            dir.path(),
+        );
+        assert!(Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(dir.path())
+            .status()
+            .unwrap()
+            .success());
+        Self {
+            dir,
+            state: TempDir::new().unwrap(),
+        }
+    }
+
+    fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_revet"));
+        command
+            .args(args)
+            .current_dir(self.path())
+            .env_remove("REVET_DATA_DIR")
+            .env_remove("REVET_LOCALE")
+            .env("XDG_STATE_HOME", self.state.path())
+            .env("NO_COLOR", "1");
+        for var in CI_VARS {
+            command.env_remove(var);
+        }
+        command
+    }
+
+    fn run(&self, args: &[&str]) -> Output {
+        let output = self.command(args).output().unwrap();
+        assert!(
+            output.status.success(),
+            "{:?}: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output
+    }
+
+    fn review(&self, extra: &[&str]) -> Value {
+        let mut args = vec!["review", "--full", "--format", "json", "--fail-on", "never"];
+        args.extend(extra);
+        serde_json::from_slice(&self.run(&args).stdout).unwrap()
+    }
+
+    fn review_in_ci(&self) -> Value {
+        let output = self
+            .command(&["review", "--full", "--format", "json", "--fail-on", "never"])
+            .env("CI", "true")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).unwrap()
+    }
+}
+
+fn findings(report: &Value) -> Vec<(String, String)> {
+    report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| {
+            (
+                f["message"].as_str().unwrap().to_string(),
+                f["file"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Every file under `root`, `.git/` aside
+fn files(root: &Path) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.ends_with(".git") {
+                continue;
+            }
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                out.push(path);
+            }
+        }
+    }
+    out.sort();
+    out
+}
+
+#[test]
+fn test_ignored_finding_is_hidden_and_counted() {
+    let repo = Repo::new();
+    let first = repo.review(&[]);
+    let all = findings(&first);
+    assert!(all.len() >= 2, "{:?}", all);
+    assert!(first["summary"].get("session_ignored").is_none());
+    let id = first["findings"][0]["id"].as_str().unwrap().to_string();
+
+    let before = files(repo.path());
+    let add = repo.run(&["ignore", "add", &id, "--ttl", "8h"]);
+    assert!(String::from_utf8_lossy(&add.stderr).contains("for 8h"));
+    // Nothing in the repository, the entry is under the state home
+    assert_eq!(files(repo.path()), before);
+    assert_eq!(files(repo.state.path()).len(), 1);
+
+    let second = repo.review(&[]);
+    assert_eq!(findings(&second), all[1..]);
+    assert_eq!(second["summary"]["session_ignored"], 1);
+
+    let list = repo.run(&["ignore", "list"]);
+    let listed = String::from_utf8_lossy(&list.stdout);
+    assert!(listed.contains(&id), "{}", listed);
+    assert!(listed.contains("7h 59m left"), "{}", listed);
+
+    let cleared = repo.run(&["ignore", "clear"]);
+    assert!(String::from_utf8_lossy(&cleared.stderr).contains("1 session ignore(s) cleared"));
+    let third = repo.review(&[]);
+    assert_eq!(findings(&third), all);
+    assert!(third["summary"].get("session_ignored").is_none());
+}
+
+#[test]
+fn test_ci_and_flag_skip_session_ignores() {
+    let repo = Repo::new();
+    let all = findings(&repo.review(&[]));
+    repo.run(&["ignore", "add", "--path", "**"]);
+    assert!(findings(&repo.review(&[])).is_empty());
+
+    let ci = repo.review_in_ci();
+    assert_eq!(findings(&ci), all);
+    assert!(ci["summary"].get("session_ignored").is_none());
+
+    let flagged = repo.review(&["--no-session-ignores"]);
+    assert_eq!(findings(&flagged), all);
+    assert!(flagged["summary"].get("session_ignored").is_none());
+}
+
+#[test]
+fn test_session_ignores_apply_after_the_baseline() {
+    let repo = Repo::new();
+    let all = findings(&repo.review(&[]));
+    repo.run(&["baseline"]);
+    repo.run(&["ignore", "add", "--path", "**"]);
+
+    // The baseline takes every finding first; the entries were active
+    let report = repo.review(&[]);
+    assert!(findings(&report).is_empty());
+    assert_eq!(report["summary"]["session_ignored"], 0);
+
+    let unbaselined = repo.review(&["--no-baseline"]);
+    assert!(findings(&unbaselined).is_empty());
+    assert_eq!(unbaselined["summary"]["session_ignored"], all.len());
+}
+
+#[test]
+fn test_entries_expire() {
+    let repo = Repo::new();
+    let all = findings(&repo.review(&[]));
+    repo.run(&["ignore", "add", "--path", "**", "--ttl", "1s"]);
+    std::thread::sleep(std::time::Duration::from_millis(2100));
+
+    let report = repo.review(&[]);
+    assert_eq!(findings(&report), all);
+    assert!(report["summary"].get("session_ignored").is_none());
+    let list = repo.run(&["ignore", "list"]);
+    assert!(String::from_utf8_lossy(&list.stdout).contains("No session ignores."));
+}
+
+#[test]
+fn test_unknown_id_is_a_usage_error() {
+    let repo = Repo::new();
+    let missing = repo
+        .command(&["ignore", "add", "SEC-999"])
+        .output()
+        .unwrap();
+    assert_eq!(missing.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("revet review"));
+
+    repo.review(&[]);
+    let unknown = repo
+        .command(&["ignore", "add", "SEC-999"])
+        .output()
+        .unwrap();
+    assert_eq!(unknown.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&unknown.stderr).contains("not in the last run"));
+}
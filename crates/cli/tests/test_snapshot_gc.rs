@@ -0,0 +1,83 @@
+use revet_cli::commands::snapshot;
+use revet_core::graph::{Node, NodeData, NodeKind};
+use revet_core::store::create_store;
+use revet_core::{CodeGraph, GraphStore};
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+fn graph(version: usize) -> CodeGraph {
+    let mut graph = CodeGraph::new(PathBuf::from("/repo"));
+    graph.add_node(Node::new(
+        NodeKind::Function,
+        format!("func_{version}"),
+        PathBuf::from("a.py"),
+        1,
+        NodeData::Function {
+            parameters: vec![],
+            return_type: None,
+        },
+    ));
+    graph
+}
+
+/// A repository whose graph store holds snapshots `s0`..`s{count - 1}`
+fn repo(config: &str, count: usize) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join(".revet.toml"), config).unwrap();
+    let store = create_store(dir.path()).unwrap();
+    for v in 0..count {
+        store.flush(&graph(v), &format!("s{v}")).unwrap();
+    }
+    dir
+}
+
+fn snapshot_names(dir: &TempDir) -> Vec<String> {
+    let mut names: Vec<String> = create_store(dir.path())
+        .unwrap()
+        .snapshots()
+        .unwrap()
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_gc_applies_configured_retention() {
+    let dir = repo("[cache]\nkeep_last = 2\n", 4);
+    let report = snapshot::gc(Some(dir.path()), None, None).unwrap();
+    assert_eq!(report.removed, vec!["s0", "s1"]);
+    assert_eq!(snapshot_names(&dir), vec!["s2", "s3"]);
+}
+
+#[test]
+fn test_flag_overrides_configured_retention() {
+    let dir = repo("[cache]\nkeep_last = 3\n", 4);
+    let report = snapshot::gc(Some(dir.path()), Some(1), None).unwrap();
+    assert_eq!(report.removed.len(), 3);
+    assert_eq!(snapshot_names(&dir), vec!["s3"]);
+}
+
+#[test]
+fn test_gc_without_policy_keeps_everything() {
+    let dir = repo("", 3);
+    let report = snapshot::gc(Some(dir.path()), None, None).unwrap();
+    assert!(report.removed.is_empty());
+    assert_eq!(snapshot_names(&dir).len(), 3);
+}
+
+#[test]
+fn test_gc_without_store_does_nothing() {
+    let dir = TempDir::new().unwrap();
+    let report = snapshot::gc(Some(dir.path()), Some(1), None).unwrap();
+    assert_eq!(report.kept, 0);
+    assert!(!dir.path().join(".revet-cache").exists());
+}
+
+#[test]
+fn test_keep_last_zero_is_rejected() {
+    let dir = repo("", 1);
+    assert!(snapshot::gc(Some(dir.path()), Some(0), None).is_err());
+}
@@ -0,0 +1,180 @@
+//! State outside the repository: `REVET_DATA_DIR`, the legacy
+//! `.revet-cache/` fallback and `revet storage migrate`
+
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn copy_tree(from: &Path, to: &Path) {
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            fs::create_dir_all(&target).unwrap();
+            copy_tree(&entry.path(), &target);
+        } else {
+            fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+/// The `api_dashboard` fixture as a git repository
+fn repo() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    copy_tree(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/api_dashboard"),
+        tmp.path(),
+    );
+    assert!(Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(tmp.path())
+        .status()
+        .unwrap()
+        .success());
+    tmp
+}
+
+fn revet(repo: &Path, data_dir: Option<&Path>, args: &[&str]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_revet"));
+    command
+        .args(args)
+        .current_dir(repo)
+        .env_remove("REVET_DATA_DIR")
+        .env_remove("REVET_LOCALE")
+        .env_remove("GITHUB_ACTIONS")
+        .env("NO_COLOR", "1");
+    if let Some(dir) = data_dir {
+        command.env("REVET_DATA_DIR", dir);
+    }
+    command.output().unwrap()
+}
+
+fn review(repo: &Path, data_dir: Option<&Path>) -> Value {
+    let output = revet(
+        repo,
+        data_dir,
+        &["review", "--full", "--format", "json", "--fail-on", "never"],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+/// Every path under `root`, relative and sorted, `.git/` aside
+fn tree(root: &Path) -> Vec<PathBuf> {
+    fn walk(dir: &Path, root: &Path, out: &mut Vec<PathBuf>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let rel = path.strip_prefix(root).unwrap().to_path_buf();
+            if rel.starts_with(".git") {
+                continue;
+            }
+            if path.is_dir() {
+                walk(&path, root, out);
+            }
+            out.push(rel);
+        }
+    }
+    let mut out = Vec::new();
+    walk(root, root, &mut out);
+    out.sort();
+    out
+}
+
+#[cfg(unix)]
+fn set_read_only(root: &Path, read_only: bool) {
+    use std::os::unix::fs::PermissionsExt;
+    for rel in tree(root).into_iter().chain([PathBuf::new()]) {
+        let path = root.join(rel);
+        let mode = match (path.is_dir(), read_only) {
+            (true, true) => 0o555,
+            (true, false) => 0o755,
+            (false, true) => 0o444,
+            (false, false) => 0o644,
+        };
+        fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+    }
+}
+
+/// The one per-repository directory `REVET_DATA_DIR` holds
+fn state_dir(data: &Path) -> PathBuf {
+    let dirs: Vec<PathBuf> = fs::read_dir(data)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .collect();
+    assert_eq!(dirs.len(), 1, "{:?}", dirs);
+    dirs.into_iter().next().unwrap()
+}
+
+#[cfg(unix)]
+#[test]
+fn test_review_of_read_only_checkout() {
+    let repo = repo();
+    let data = TempDir::new().unwrap();
+    let before = tree(repo.path());
+    set_read_only(repo.path(), true);
+
+    // Running as root ignores the permissions, so the tree comparison below
+    // is what proves nothing was written into the checkout
+    let first = review(repo.path(), Some(data.path()));
+    assert!(!first["findings"].as_array().unwrap().is_empty());
+    let baseline = revet(repo.path(), Some(data.path()), &["baseline"]);
+    assert!(
+        baseline.status.success(),
+        "{}",
+        String::from_utf8_lossy(&baseline.stderr)
+    );
+    let second = review(repo.path(), Some(data.path()));
+    assert_eq!(second["findings"].as_array().unwrap().len(), 0);
+
+    set_read_only(repo.path(), false);
+    assert_eq!(tree(repo.path()), before);
+
+    let state = state_dir(data.path());
+    let name = state.file_name().unwrap().to_string_lossy().into_owned();
+    let repo_name = repo.path().file_name().unwrap().to_string_lossy();
+    assert!(name.starts_with(&format!("{}-", repo_name)), "{}", name);
+    for entry in ["graph.meta.json", "graph.db", "files", "baseline.json"] {
+        assert!(state.join(entry).exists(), "{} missing", entry);
+    }
+    assert_eq!(fs::read_dir(state.join("runs")).unwrap().count(), 2);
+}
+
+#[test]
+fn test_legacy_state_is_read_until_migrated() {
+    let repo = repo();
+    let data = TempDir::new().unwrap();
+    review(repo.path(), None);
+    let legacy = repo.path().join(".revet-cache");
+    let run = fs::read_dir(legacy.join("runs"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let id = run.file_stem().unwrap().to_string_lossy().into_owned();
+
+    // The in-repo run log is still listed once a data directory is set
+    let log = revet(repo.path(), Some(data.path()), &["log"]);
+    let listed = String::from_utf8_lossy(&log.stdout);
+    assert!(listed.contains(&id), "{}", listed);
+
+    let migrate = revet(repo.path(), Some(data.path()), &["storage", "migrate"]);
+    let stderr = String::from_utf8_lossy(&migrate.stderr);
+    assert!(migrate.status.success(), "{}", stderr);
+    assert!(stderr.contains("moved runs"), "{}", stderr);
+    assert!(!legacy.exists());
+    let state = state_dir(data.path());
+    assert_eq!(fs::read_dir(state.join("runs")).unwrap().count(), 1);
+    assert!(state.join("graph.db").exists());
+
+    // Without a data directory there is nowhere to migrate to
+    let unconfigured = revet(repo.path(), None, &["storage", "migrate"]);
+    assert!(!unconfigured.status.success());
+    assert!(String::from_utf8_lossy(&unconfigured.stderr).contains("REVET_DATA_DIR"));
+}
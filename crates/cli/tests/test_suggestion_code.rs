@@ -0,0 +1,102 @@
+use clap::Parser;
+use revet_cli::commands::review;
+use revet_cli::output::github_comment::format_comment_body;
+use revet_cli::Cli;
+use revet_core::{Finding, InsertContext, Locale, Severity, SuggestedCode};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+fn cli(args: &[&str]) -> Cli {
+    let mut argv = vec!["revet"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+fn repo(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join(".revet.toml"), "[modules]\nreact = true\n").unwrap();
+    for (name, content) in files {
+        std::fs::write(dir.path().join(name), content).unwrap();
+    }
+    dir
+}
+
+fn code_of<'a>(run: &'a review::ReviewRun, rule: &str) -> Option<&'a SuggestedCode> {
+    let finding = run
+        .findings
+        .iter()
+        .find(|f| f.rule.as_deref() == Some(rule))
+        .unwrap_or_else(|| panic!("no {} finding in {:?}", rule, run.findings));
+    finding.suggestion_code.as_ref()
+}
+
+#[test]
+fn test_review_keeps_code_that_parses() {
+    let dir = repo(&[
+        (
+            "App.tsx",
+            "export function App({ items }) {\n  useEffect(() => {\n    load();\n  });\n  return <ul>{items.map(item => <Row name={item.name} />)}</ul>;\n}\n",
+        ),
+        (
+            "db.py",
+            "def load(cursor, uid):\n    cursor.execute(f\"SELECT * FROM users WHERE id = {uid}\")\n",
+        ),
+    ]);
+    let run = review::analyze(dir.path(), &cli(&["--full"])).unwrap();
+
+    let effect = code_of(&run, "react:effect_without_deps").unwrap();
+    assert_eq!(effect.snippet, "useEffect(() => {\n    load();\n  }, [])");
+    let key = code_of(&run, "react:missing_key").unwrap();
+    assert!(key
+        .snippet
+        .contains("<Row key={item.id} name={item.name} />"));
+    let sql = code_of(&run, "security:sql_injection").unwrap();
+    assert_eq!(
+        sql.snippet,
+        "cursor.execute(\"SELECT * FROM users WHERE id = ?\", (uid,))"
+    );
+    assert!(run.suggestions.demoted.is_empty(), "{:?}", run.suggestions);
+    assert_eq!(run.suggestions.checked["security:sql_injection"], 1);
+}
+
+#[test]
+fn test_review_demotes_code_that_does_not_parse() {
+    // The stray paren carries over into the rewritten call
+    let dir = repo(&[(
+        "db.py",
+        "def load(cursor, uid):\n    cursor.execute(f\"SELECT * FROM users WHERE id = {uid}\"))\n",
+    )]);
+    let run = review::analyze(dir.path(), &cli(&["--full"])).unwrap();
+
+    assert!(code_of(&run, "security:sql_injection").is_none());
+    let finding = run
+        .findings
+        .iter()
+        .find(|f| f.rule.as_deref() == Some("security:sql_injection"))
+        .unwrap();
+    assert!(finding.suggestion.is_some());
+    assert_eq!(run.suggestions.demoted["security:sql_injection"], 1);
+    assert_eq!(run.suggestions.demoted_total(), 1);
+}
+
+#[test]
+fn test_comment_body_fences_the_code() {
+    let finding = Finding {
+        id: "SEC-001".to_string(),
+        severity: Severity::Error,
+        message: "Hardcoded AWS access key".to_string(),
+        file: PathBuf::from("config.py"),
+        line: 3,
+        suggestion: Some("Read the key from the environment".to_string()),
+        suggestion_code: Some(SuggestedCode {
+            language: "python".to_string(),
+            snippet: "AWS_KEY = os.environ[\"AWS_ACCESS_KEY_ID\"]".to_string(),
+            insert_context: InsertContext::Line,
+        }),
+        ..Default::default()
+    };
+    assert_eq!(
+        format_comment_body(&finding, Locale::En),
+        "🔴 **revet [SEC-001]**: Hardcoded AWS access key\n\n> **Suggestion:** Read the key from the environment\n\n```python\nAWS_KEY = os.environ[\"AWS_ACCESS_KEY_ID\"]\n```\n\n<!-- revet:SEC-001 -->"
+    );
+}
@@ -0,0 +1,130 @@
+use clap::Parser;
+use revet_cli::commands::review;
+use revet_cli::Cli;
+use revet_core::schedule::PhaseTiming;
+use revet_core::{StoragePaths, Timings};
+use tempfile::TempDir;
+
+fn cli(args: &[&str]) -> Cli {
+    let mut argv = vec!["revet"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+/// One file with a hardcoded secret (content analyzer) and an unused
+/// function (graph analyzer)
+fn repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join(".revet.toml"),
+        "[modules]\nduplication = true\ndead_code = true\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("app.py"),
+        "def orphan(x):\n    return x * 2\n\n\ndef main():\n    password = \"hunter2-Secret-Value\"\n    print(password)\n",
+    )
+    .unwrap();
+    dir
+}
+
+/// Store the timings a previous, slower run of this one file would have left
+fn store_timings(dir: &TempDir) {
+    let mut timings = Timings::default();
+    for (key, secs) in [
+        ("security", 2.0),
+        ("ml", 1.0),
+        ("graph", 30.0),
+        ("dead_code", 5.0),
+        ("cycles", 1.0),
+        ("duplication", 190.0),
+    ] {
+        timings
+            .phases
+            .insert(key.to_string(), PhaseTiming { secs, files: 1 });
+    }
+    timings.save(&StoragePaths::in_repo(dir.path())).unwrap();
+}
+
+fn has(run: &review::ReviewRun, prefix: &str) -> bool {
+    run.findings.iter().any(|f| f.id.starts_with(prefix))
+}
+
+fn skipped(run: &review::ReviewRun) -> Vec<String> {
+    let report = run.summary.budget.as_ref().unwrap();
+    report
+        .skipped_phases
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+#[test]
+fn test_without_budget_nothing_is_reported() {
+    let dir = repo();
+    let run = review::analyze(dir.path(), &cli(&["--full"])).unwrap();
+    assert!(run.summary.budget.is_none());
+    assert!(has(&run, "SEC") && has(&run, "DEAD"));
+    // Timings are stored for the next budgeted run
+    let timings = Timings::load(&StoragePaths::in_repo(dir.path())).unwrap();
+    assert!(timings.phases.contains_key("graph"));
+    assert!(timings.phases.contains_key("security"));
+}
+
+#[test]
+fn test_budget_skips_the_scan_that_no_longer_fits() {
+    let dir = repo();
+    store_timings(&dir);
+    let run = review::analyze(dir.path(), &cli(&["--full", "--time-budget", "2m"])).unwrap();
+
+    let report = run.summary.budget.as_ref().unwrap();
+    assert!(report.budget_exceeded);
+    assert_eq!(report.time_budget_secs, 120.0);
+    let skipped = skipped(&run);
+    assert!(
+        skipped
+            .iter()
+            .any(|s| s.starts_with("duplication analyzer, estimated 190s, ")),
+        "{:?}",
+        skipped
+    );
+    assert!(!skipped.iter().any(|s| s.starts_with("code graph")));
+    assert!(run.summary.nodes_parsed > 0);
+    assert!(has(&run, "SEC") && has(&run, "DEAD"));
+}
+
+#[test]
+fn test_budget_skips_the_graph_and_its_analyzers() {
+    let dir = repo();
+    store_timings(&dir);
+    let run = review::analyze(dir.path(), &cli(&["--full", "--time-budget", "20s"])).unwrap();
+
+    let skipped = skipped(&run);
+    assert!(
+        skipped
+            .iter()
+            .any(|s| s.starts_with("code graph, estimated 30s")),
+        "{:?}",
+        skipped
+    );
+    assert!(skipped.contains(&"dead code analyzer, needs the code graph".to_string()));
+    assert_eq!(run.summary.nodes_parsed, 0);
+    assert!(has(&run, "SEC"));
+    assert!(!has(&run, "DEAD"));
+}
+
+#[test]
+fn test_zero_budget_still_produces_a_review() {
+    let dir = repo();
+    store_timings(&dir);
+    let run = review::analyze(dir.path(), &cli(&["--full", "--time-budget", "0"])).unwrap();
+
+    let report = run.summary.budget.as_ref().unwrap();
+    assert!(report.budget_exceeded);
+    assert!(run.findings.is_empty(), "{:?}", run.findings);
+    assert!(run.run_id.is_some());
+    // Skipped phases keep their previous timings
+    let timings = Timings::load(&StoragePaths::in_repo(dir.path())).unwrap();
+    assert_eq!(timings.phases["duplication"].secs, 190.0);
+    assert_eq!(timings.phases["graph"].secs, 30.0);
+}
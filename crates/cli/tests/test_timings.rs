@@ -0,0 +1,76 @@
+use clap::Parser;
+use revet_cli::commands::review;
+use revet_cli::{Cli, TimingsDetail};
+use tempfile::TempDir;
+
+fn cli(args: &[&str]) -> Cli {
+    let mut argv = vec!["revet"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+fn repo(config: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join(".revet.toml"), config).unwrap();
+    for name in ["a.py", "b.py"] {
+        std::fs::write(
+            dir.path().join(name),
+            "def main(cursor, uid):\n    cursor.execute(f\"SELECT * FROM t WHERE id = {uid}\")\n",
+        )
+        .unwrap();
+    }
+    dir
+}
+
+#[test]
+fn test_timings_flag_takes_an_optional_detail() {
+    assert_eq!(cli(&["review"]).timings, None);
+    assert_eq!(
+        cli(&["review", "--timings"]).timings,
+        Some(TimingsDetail::Summary)
+    );
+    assert_eq!(
+        cli(&["review", "--timings=full"]).timings,
+        Some(TimingsDetail::Full)
+    );
+    // `--timings` does not swallow the path
+    assert_eq!(
+        cli(&["review", "--timings", "."]).timings,
+        Some(TimingsDetail::Summary)
+    );
+}
+
+#[test]
+fn test_full_timings_reach_the_summary() {
+    let dir = repo("");
+    let run = review::analyze(dir.path(), &cli(&["--full", "--timings=full"])).unwrap();
+    let report = run.summary.timings.as_ref().unwrap();
+    let sql = report.analyzers.iter().find(|a| a.prefix == "SQL").unwrap();
+    assert_eq!(sql.findings, 2);
+    let mut files: Vec<&str> = sql.slowest_files.iter().map(|f| f.file.as_str()).collect();
+    files.sort();
+    assert_eq!(files, vec!["a.py", "b.py"]);
+    // No threshold, no findings about it
+    assert!(!run.findings.iter().any(|f| f.id.starts_with("PERF")));
+
+    let run = review::analyze(dir.path(), &cli(&["--full", "--timings"])).unwrap();
+    let report = run.summary.timings.as_ref().unwrap();
+    assert!(report.analyzers.iter().all(|a| a.slowest_files.is_empty()));
+    let run = review::analyze(dir.path(), &cli(&["--full"])).unwrap();
+    assert!(run.summary.timings.is_none());
+}
+
+#[test]
+fn test_slow_file_threshold_flags_files_in_normal_runs() {
+    let dir = repo("[performance]\nslow_file_warn_ms = 0\n");
+    let run = review::analyze(dir.path(), &cli(&["--full"])).unwrap();
+    let slow: Vec<_> = run
+        .findings
+        .iter()
+        .filter(|f| f.id.starts_with("PERF"))
+        .collect();
+    assert_eq!(slow.len(), 2, "{:?}", run.findings);
+    assert!(slow
+        .iter()
+        .all(|f| f.severity == revet_core::Severity::Info && f.line == 1));
+}
@@ -0,0 +1,89 @@
+//! `revet upgrade-report` on the upgrade fixture: inferred and explicit
+//! targets, JSON and terminal output, exit codes
+
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn fixture() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures/upgrade")
+        .canonicalize()
+        .unwrap()
+}
+
+fn revet(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_revet"))
+        .arg("upgrade-report")
+        .arg(fixture())
+        .args(args)
+        .env_remove("REVET_LOCALE")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_json_report_for_an_explicit_target() {
+    let output = revet(&["--target", "python=3.12", "--format", "json"]);
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let runtimes = report["runtimes"].as_array().unwrap();
+    assert_eq!(runtimes.len(), 1, "{:#}", report);
+    let python = &runtimes[0];
+    assert_eq!(python["runtime"], "python");
+    assert_eq!(python["targets"][0], "3.12");
+    assert_eq!(python["ready"], false);
+    assert_eq!(python["blockers"], 1);
+    assert_eq!(python["warnings"], 0);
+    assert_eq!(python["modules"][0]["module"], "distutils");
+
+    let usage = &python["usages"][0];
+    assert_eq!(usage["api"], "distutils");
+    assert_eq!(usage["status"], "removed");
+    assert_eq!(usage["version"], "3.12");
+    assert_eq!(usage["file"], "python/release.py");
+    assert_eq!(usage["line"], 4);
+}
+
+#[test]
+fn test_terminal_report_with_inferred_targets() {
+    let output = revet(&[]);
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Node.js → 20  ready, 1 warning(s)"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Python → 3.11  ready, 1 warning(s)"),
+        "{}",
+        stdout
+    );
+
+    let output = revet(&["--target", "node=22"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Node.js → 22  1 blocker(s)"), "{}", stdout);
+    assert!(
+        stdout.contains("node/vault.js:8  `crypto.createCipher` removed in 22"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Python"), "{}", stdout);
+}
+
+#[test]
+fn test_bad_targets_and_formats_are_usage_errors() {
+    for args in [
+        &["--target", "python"][..],
+        &["--target", "python=latest"],
+        &["--target", "cobol=85"],
+        &["--format", "sarif"],
+    ] {
+        let output = revet(args);
+        assert_eq!(output.status.code(), Some(2), "{:?}: {:?}", args, output);
+    }
+}
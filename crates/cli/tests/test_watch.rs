@@ -0,0 +1,224 @@
+use revet_cli::commands::watch::{event_loop, status_line, Action, Event, Notice, Supervisor};
+use revet_core::{CancelToken, Cancelled, MemoStats};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn started<T>(action: Option<Action<T>>) -> (u64, CancelToken) {
+    match action {
+        Some(Action::Start { run, token }) => (run, token),
+        _ => panic!("expected a run to start"),
+    }
+}
+
+/// The runs a list of actions starts and the notices it reports, as text
+fn describe<T: std::fmt::Debug>(actions: Vec<Action<T>>) -> Vec<String> {
+    actions
+        .into_iter()
+        .map(|action| match action {
+            Action::Start { run, .. } => format!("start {}", run),
+            Action::Notify(notice) => format!("{:?}", notice),
+        })
+        .collect()
+}
+
+// ── Supervisor ───────────────────────────────────────────────────
+
+#[test]
+fn test_changes_during_run_coalesce_into_one_rerun() {
+    let mut supervisor = Supervisor::new();
+    let (run, token) = started::<&str>(supervisor.on_change());
+    assert_eq!(run, 1);
+
+    for _ in 0..5 {
+        assert!(supervisor.on_change::<&str>().is_none());
+    }
+    assert!(token.is_cancelled());
+
+    // Even a run that got to the end is dropped once superseded
+    assert_eq!(
+        describe(supervisor.on_finished(1, Ok("stale"))),
+        vec!["Superseded { run: 1 }", "start 2"]
+    );
+    assert_eq!(
+        describe(supervisor.on_finished(2, Ok("fresh"))),
+        vec![r#"Completed { run: 2, result: "fresh" }"#]
+    );
+    assert!(!supervisor.is_running());
+}
+
+#[test]
+fn test_idle_change_starts_immediately() {
+    let mut supervisor = Supervisor::new();
+    started::<()>(supervisor.on_change());
+    assert_eq!(describe(supervisor.on_finished(1, Ok(()))).len(), 1);
+
+    let (run, token) = started::<()>(supervisor.on_change());
+    assert_eq!(run, 2);
+    assert!(!token.is_cancelled());
+}
+
+#[test]
+fn test_failures_and_cancellations() {
+    let mut supervisor = Supervisor::new();
+    started::<()>(supervisor.on_change());
+    let actions = supervisor.on_finished(1, Err::<(), _>(anyhow::anyhow!("bad config")));
+    assert!(matches!(
+        &actions[..],
+        [Action::Notify(Notice::Failed { run: 1, error })] if error.to_string() == "bad config"
+    ));
+
+    started::<()>(supervisor.on_change());
+    supervisor.on_change::<()>();
+    let actions = supervisor.on_finished(2, Err::<(), _>(Cancelled.into()));
+    assert_eq!(describe(actions), vec!["Superseded { run: 2 }", "start 3"]);
+}
+
+#[test]
+fn test_unknown_run_is_ignored() {
+    let mut supervisor = Supervisor::new();
+    started::<()>(supervisor.on_change());
+    assert!(supervisor.on_finished(7, Ok(())).is_empty());
+    assert!(supervisor.is_running());
+
+    supervisor.shutdown();
+    assert_eq!(
+        describe(supervisor.on_finished(1, Ok(()))),
+        vec!["Superseded { run: 1 }"]
+    );
+}
+
+// ── Event loop ───────────────────────────────────────────────────
+
+/// Analysis that takes `duration`, checking its token every 5ms, and
+/// returns the repository version it started from
+fn slow_analysis(
+    version: Arc<AtomicUsize>,
+    duration: Duration,
+) -> impl Fn(&CancelToken) -> anyhow::Result<usize> + Sync {
+    move |token| {
+        let seen = version.load(Ordering::SeqCst);
+        let start = Instant::now();
+        while start.elapsed() < duration {
+            token.check()?;
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        Ok(seen)
+    }
+}
+
+#[test]
+fn test_rapid_saves_complete_one_analysis_with_latest_state() {
+    let (tx, rx) = mpsc::channel();
+    let (notices_tx, notices) = mpsc::channel();
+    let running = Arc::new(AtomicBool::new(true));
+    let version = Arc::new(AtomicUsize::new(0));
+
+    let worker = {
+        let (tx, running, version) = (tx.clone(), running.clone(), version.clone());
+        std::thread::spawn(move || {
+            event_loop(
+                rx,
+                tx,
+                slow_analysis(version, Duration::from_millis(300)),
+                |notice| notices_tx.send((Instant::now(), notice)).unwrap(),
+                &running,
+            )
+        })
+    };
+    let next = || notices.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    // First save starts a run; five more arrive while it is in flight
+    let mut saves = Vec::new();
+    for i in 0..6 {
+        if i > 0 {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        version.fetch_add(1, Ordering::SeqCst);
+        saves.push(Instant::now());
+        tx.send(Event::Changed).unwrap();
+    }
+
+    // Every run but the last is superseded, soon after the save that
+    // overtook it; only the last run completes, with the latest state
+    let mut expected_run = 1;
+    loop {
+        assert!(matches!(next().1, Notice::Started { run } if run == expected_run));
+        let (at, notice) = next();
+        match notice {
+            Notice::Superseded { run } => {
+                assert_eq!(run, expected_run);
+                let save = saves.iter().rev().find(|&&s| s <= at).unwrap();
+                assert!(
+                    at.duration_since(*save) < Duration::from_millis(200),
+                    "cancellation took {:?}",
+                    at.duration_since(*save)
+                );
+            }
+            Notice::Completed { run, result } => {
+                assert_eq!(run, expected_run);
+                assert_eq!(result, 6);
+                break;
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        expected_run += 1;
+    }
+    assert!(expected_run <= 6);
+
+    running.store(false, Ordering::SeqCst);
+    worker.join().unwrap();
+    assert!(notices.try_recv().is_err(), "no further runs");
+}
+
+#[test]
+fn test_stop_cancels_run_in_flight() {
+    let (tx, rx) = mpsc::channel();
+    let running = Arc::new(AtomicBool::new(true));
+    let version = Arc::new(AtomicUsize::new(0));
+    let (notices_tx, notices) = mpsc::channel();
+
+    tx.send(Event::Changed).unwrap();
+    let worker = {
+        let running = running.clone();
+        std::thread::spawn(move || {
+            event_loop(
+                rx,
+                tx,
+                slow_analysis(version, Duration::from_secs(30)),
+                |notice| notices_tx.send(notice).unwrap(),
+                &running,
+            )
+        })
+    };
+    assert!(matches!(
+        notices.recv_timeout(Duration::from_secs(5)).unwrap(),
+        Notice::Started { run: 1 }
+    ));
+
+    let stopped = Instant::now();
+    running.store(false, Ordering::SeqCst);
+    worker.join().unwrap();
+    assert!(stopped.elapsed() < Duration::from_secs(1));
+}
+
+// ── Status line ──────────────────────────────────────────────────
+
+#[test]
+fn test_status_line_shows_memo_hits() {
+    assert_eq!(
+        status_line(None),
+        "Watching for changes... (Ctrl-C to stop)"
+    );
+    let memo = MemoStats {
+        hits: 38,
+        misses: 2,
+        evictions: 1,
+        ..MemoStats::default()
+    };
+    assert_eq!(
+        status_line(Some(&memo)),
+        "Watching for changes... (Ctrl-C to stop) · memo 95% hits, 1 evicted"
+    );
+}
@@ -0,0 +1,78 @@
+//! Overhead of per-file timing: `cargo bench -p revet-core --bench file_timings`
+//!
+//! Runs the content analyzers over a synthetic repository three ways: each
+//! analyzer called directly on a rayon task (the baseline), through the
+//! dispatcher with per-file timing off, and with it on. Off should match
+//! the baseline within noise.
+
+use rayon::prelude::*;
+use revet_core::config::RevetConfig;
+use revet_core::AnalyzerDispatcher;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+const FILES: usize = 400;
+const ROUNDS: usize = 15;
+
+fn median(mut samples: Vec<Duration>) -> Duration {
+    samples.sort();
+    samples[samples.len() / 2]
+}
+
+fn measure(mut f: impl FnMut() -> usize) -> (Duration, usize) {
+    let mut findings = f();
+    let samples = (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            findings = f();
+            start.elapsed()
+        })
+        .collect();
+    (median(samples), findings)
+}
+
+fn main() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let files: Vec<PathBuf> = (0..FILES)
+        .map(|i| {
+            let path = dir.path().join(format!("module_{i}.py"));
+            let body: String = (0..60)
+                .map(|n| {
+                    format!(
+                        "def handler_{n}(request):\n    query = f\"SELECT * FROM t WHERE id = {{request.id}}\"\n    return db.execute(query)\n\n"
+                    )
+                })
+                .collect();
+            std::fs::write(&path, body).unwrap();
+            path
+        })
+        .collect();
+    let root = dir.path();
+    let config = RevetConfig::default();
+
+    let off = AnalyzerDispatcher::new_with_config(&config);
+    let on = AnalyzerDispatcher::new_with_config(&config).with_file_timings(true);
+    let enabled: Vec<_> = off
+        .prefixes()
+        .filter_map(|prefix| off.analyzer(prefix))
+        .filter(|a| a.is_enabled(&config))
+        .collect();
+
+    let (baseline, n0) = measure(|| {
+        enabled
+            .par_iter()
+            .map(|a| a.analyze_files(&files, root).len())
+            .sum()
+    });
+    let (timing_off, n1) = measure(|| off.run_all_parallel_timed(&files, root, &config).0.len());
+    let (timing_on, n2) = measure(|| on.run_all_parallel_timed(&files, root, &config).0.len());
+
+    println!("{FILES} files, median of {ROUNDS} rounds ({n0}/{n1}/{n2} findings)");
+    println!("  baseline (direct)              {:>10.2?}", baseline);
+    println!("  dispatcher, timing off         {:>10.2?}", timing_off);
+    println!("  dispatcher, timing on          {:>10.2?}", timing_on);
+    println!(
+        "  on / off                       {:>10.3}",
+        timing_on.as_secs_f64() / timing_off.as_secs_f64()
+    );
+}
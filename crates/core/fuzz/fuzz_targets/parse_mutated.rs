@@ -0,0 +1,30 @@
+//! Structured mutation under libFuzzer: the input seeds the mutations
+//! `parser::fuzz::mutate` applies to a conformance fixture, so every input
+//! is close to valid code.
+//!
+//! `cargo +nightly fuzz run parse_mutated`
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use revet_core::parser::conformance::{self, Rng};
+use revet_core::parser::fuzz;
+use revet_core::ParserDispatcher;
+use std::sync::OnceLock;
+
+static DISPATCHER: OnceLock<ParserDispatcher> = OnceLock::new();
+
+fuzz_target!(|data: [u8; 16]| {
+    let dispatcher = DISPATCHER.get_or_init(ParserDispatcher::new);
+    let parsers: Vec<_> = dispatcher.parsers().collect();
+    let seed = u64::from_le_bytes(data[..8].try_into().unwrap());
+    let mutation = u64::from_le_bytes(data[8..].try_into().unwrap());
+    let parser = parsers[(seed % parsers.len() as u64) as usize];
+    let Some(fixture) = conformance::generate(parser.language_name(), seed % 64) else {
+        return;
+    };
+    let source = fuzz::mutate(&fixture.source, &mut Rng::new(mutation));
+    if let Err(violations) = fuzz::check(parser, &source, false) {
+        panic!("{}: {}", parser.language_name(), violations);
+    }
+});
@@ -0,0 +1,29 @@
+//! Raw fuzzer input into a parser: the first byte picks the parser (its
+//! index among the registered ones), the rest is the source.
+//!
+//! Seed the corpus with `cargo xtask fuzz-corpus`, then
+//! `cargo +nightly fuzz run parse_source`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use revet_core::parser::fuzz;
+use revet_core::ParserDispatcher;
+use std::sync::OnceLock;
+
+static DISPATCHER: OnceLock<ParserDispatcher> = OnceLock::new();
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, source)) = data.split_first() else {
+        return;
+    };
+    let Ok(source) = std::str::from_utf8(source) else {
+        return;
+    };
+    let dispatcher = DISPATCHER.get_or_init(ParserDispatcher::new);
+    let parsers: Vec<_> = dispatcher.parsers().collect();
+    let parser = parsers[selector as usize % parsers.len()];
+    if let Err(violations) = fuzz::check(parser, source, false) {
+        panic!("{}: {}", parser.language_name(), violations);
+    }
+});
@@ -0,0 +1,314 @@
+//! The source sent to the model with each finding
+//!
+//! A finding inside a function (the innermost graph `Function` node around
+//! its line) is sent with the whole function when it fits the budget. A
+//! longer function is cut down to its signature, the lines around the
+//! finding and the lines that define identifiers the finding line uses. A
+//! finding at module level, or in a file the graph doesn't cover, is sent
+//! with the lines around it. When the finding line uses a name the file
+//! imports, the file's import lines come first.
+//!
+//! ```text
+//! [function fetch_orders, lines 10-58, excerpt]
+//!       10: def fetch_orders(client, customer):
+//!     ...
+//!       21:     url = build_url(client.host, customer)
+//!     ...
+//! >>>   30:     response = requests.get(url, verify=False)
+//! ```
+//!
+//! Every line is redacted through [`SecretIndex`] before it is added. A
+//! context never exceeds `[ai] context_tokens`: imports are dropped first,
+//! then definitions, then the window narrows to the finding line, and a
+//! line longer than the budget is cut.
+
+use std::collections::{BTreeSet, HashSet};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::{absolute, estimate_tokens, SymbolIndex};
+use crate::graph::{CodeGraph, NodeKind};
+use crate::{Finding, SecretIndex};
+
+/// `[ai] context_tokens` default
+pub const DEFAULT_CONTEXT_TOKENS: usize = 600;
+
+/// Smallest `[ai] context_tokens`: room for a function header and a line
+pub const MIN_CONTEXT_TOKENS: usize = 40;
+
+/// Lines on each side of the finding line
+const WINDOW: usize = 4;
+
+/// Lines on each side once the full window doesn't fit
+const NARROW_WINDOW: usize = 1;
+
+/// A signature longer than this is cut at it
+const MAX_SIGNATURE_LINES: usize = 6;
+
+/// An import block longer than this is cut at it
+const MAX_IMPORT_LINES: usize = 30;
+
+/// Marks the finding line
+const FINDING_MARKER: &str = ">>> ";
+
+/// Marks skipped lines
+const GAP: &str = "    ...";
+
+static IDENTIFIER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z_$][A-Za-z0-9_$]*").unwrap());
+
+static IMPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(?:import\b|from\s+[\w.]+\s+import\b|use\s+\w|using\s+[\w.]+\s*;|#include\b|(?:const|let|var)\s+.+=\s*require\()",
+    )
+    .unwrap()
+});
+
+/// Words that are never the name of something a finding is about
+const KEYWORDS: &[&str] = &[
+    "and", "as", "async", "await", "case", "catch", "class", "const", "def", "else", "except",
+    "false", "False", "fn", "for", "from", "function", "if", "import", "in", "is", "lambda", "let",
+    "match", "mut", "new", "nil", "None", "not", "null", "or", "pub", "raise", "require", "return",
+    "self", "static", "this", "throw", "true", "True", "try", "use", "var", "while", "with",
+    "yield",
+];
+
+/// Assembles the context of findings in one repository
+pub struct ContextBuilder<'a> {
+    repo_root: &'a Path,
+    secrets: &'a SecretIndex,
+    symbols: Option<SymbolIndex<'a>>,
+    max_tokens: usize,
+}
+
+/// The enclosing function of a finding
+struct Function<'g> {
+    name: &'g str,
+    lines: RangeInclusive<usize>,
+}
+
+impl<'a> ContextBuilder<'a> {
+    /// Without a graph every finding gets the lines around it
+    pub fn new(
+        repo_root: &'a Path,
+        secrets: &'a SecretIndex,
+        graph: Option<&'a CodeGraph>,
+    ) -> Self {
+        Self {
+            repo_root,
+            secrets,
+            symbols: graph.map(|graph| SymbolIndex::new(graph, repo_root)),
+            max_tokens: DEFAULT_CONTEXT_TOKENS,
+        }
+    }
+
+    /// Cap each context at `max_tokens` (`[ai] context_tokens`)
+    pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = max_tokens.max(MIN_CONTEXT_TOKENS);
+        self
+    }
+
+    /// The context of `finding`; empty for a repository-level finding or a
+    /// file that can't be read
+    pub fn build(&self, finding: &Finding) -> String {
+        if finding.file == Path::new("") || finding.line == 0 {
+            return String::new();
+        }
+        let path = absolute(self.repo_root, &finding.file);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return String::new();
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        if finding.line > lines.len() {
+            return String::new();
+        }
+
+        let function = self.function(&path, finding.line, lines.len());
+        let mut last = String::new();
+        for (header, selected) in candidates(&lines, finding.line, function.as_ref()) {
+            last = self.render(finding, &lines, header.as_deref(), &selected);
+            if estimate_tokens(&last) <= self.max_tokens {
+                return last;
+            }
+        }
+        self.cut(last)
+    }
+
+    /// The innermost function around `line` of `path`, its end clamped to
+    /// the file
+    fn function(&self, path: &Path, line: usize, len: usize) -> Option<Function<'a>> {
+        let symbols = self.symbols.as_ref()?;
+        let id = symbols.innermost(path, line, Some(NodeKind::Function))?;
+        let node = symbols.graph().node(id)?;
+        let end = node.end_line().unwrap_or(node.line()).min(len);
+        Some(Function {
+            name: node.name(),
+            lines: node.line().max(1)..=end.max(line),
+        })
+    }
+
+    fn render(
+        &self,
+        finding: &Finding,
+        lines: &[&str],
+        header: Option<&str>,
+        selected: &BTreeSet<usize>,
+    ) -> String {
+        let mut out: Vec<String> = header.map(str::to_string).into_iter().collect();
+        let mut previous: Option<usize> = None;
+        for &n in selected {
+            if previous.is_some_and(|p| n > p + 1) {
+                out.push(GAP.to_string());
+            }
+            let marker = if n == finding.line {
+                FINDING_MARKER
+            } else {
+                "    "
+            };
+            let text = self.secrets.redact_line(&finding.file, n, lines[n - 1]);
+            let line = format!("{}{:4}: {}", marker, n, text);
+            out.push(line.trim_end().to_string());
+            previous = Some(n);
+        }
+        out.join("\n")
+    }
+
+    /// Cut `text` to the budget; only a single line longer than the whole
+    /// budget gets here
+    fn cut(&self, mut text: String) -> String {
+        let mut keep = (self.max_tokens * 4).saturating_sub('…'.len_utf8());
+        while !text.is_char_boundary(keep) {
+            keep -= 1;
+        }
+        text.truncate(keep);
+        text.push('…');
+        text
+    }
+}
+
+/// What may be sent, largest first, each a header and the 1-based lines
+fn candidates(
+    lines: &[&str],
+    line: usize,
+    function: Option<&Function>,
+) -> Vec<(Option<String>, BTreeSet<usize>)> {
+    let used = identifiers(lines[line - 1]);
+    let imports = import_lines(lines, &used);
+    let window = |radius: usize, bounds: &RangeInclusive<usize>| -> BTreeSet<usize> {
+        (line.saturating_sub(radius).max(*bounds.start())..=(line + radius).min(*bounds.end()))
+            .collect()
+    };
+    let union = |parts: &[&BTreeSet<usize>]| -> BTreeSet<usize> {
+        parts.iter().flat_map(|part| part.iter().copied()).collect()
+    };
+    let only_line = BTreeSet::from([line]);
+
+    let Some(function) = function else {
+        let file = 1..=lines.len();
+        let near = window(WINDOW, &file);
+        return vec![
+            (None, union(&[&near, &imports])),
+            (None, near),
+            (None, window(NARROW_WINDOW, &file)),
+            (None, only_line),
+        ];
+    };
+
+    let body = &function.lines;
+    let whole: BTreeSet<usize> = body.clone().collect();
+    let header = format!(
+        "[function {}, lines {}-{}]",
+        function.name,
+        body.start(),
+        body.end()
+    );
+    let excerpt = Some(format!(
+        "[function {}, lines {}-{}, excerpt]",
+        function.name,
+        body.start(),
+        body.end()
+    ));
+    let signature: BTreeSet<usize> = (*body.start()..=signature_end(lines, body)).collect();
+    let near = window(WINDOW, body);
+    let first_near = near.first().copied().unwrap_or(line);
+    let definitions = definitions(lines, &used, *body.start()..first_near);
+    vec![
+        (Some(header), union(&[&whole, &imports])),
+        (
+            excerpt.clone(),
+            union(&[&signature, &definitions, &near, &imports]),
+        ),
+        (excerpt.clone(), union(&[&signature, &definitions, &near])),
+        (excerpt.clone(), union(&[&signature, &near])),
+        (excerpt.clone(), window(NARROW_WINDOW, body)),
+        (excerpt, only_line),
+    ]
+}
+
+/// Names used on a line, keywords left out
+fn identifiers(line: &str) -> HashSet<&str> {
+    IDENTIFIER_RE
+        .find_iter(line)
+        .map(|m| m.as_str())
+        .filter(|word| !KEYWORDS.contains(word))
+        .collect()
+}
+
+/// The file's import block, first import line to last, when one of them
+/// imports a name in `used`
+fn import_lines(lines: &[&str], used: &HashSet<&str>) -> BTreeSet<usize> {
+    let imports: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| IMPORT_RE.is_match(line))
+        .map(|(i, _)| i + 1)
+        .collect();
+    let (Some(&first), Some(&last)) = (imports.first(), imports.last()) else {
+        return BTreeSet::new();
+    };
+    let external = imports.iter().any(|&n| {
+        identifiers(lines[n - 1])
+            .iter()
+            .any(|name| used.contains(name))
+    });
+    if !external {
+        return BTreeSet::new();
+    }
+    (first..=last.min(first + MAX_IMPORT_LINES - 1)).collect()
+}
+
+/// The last line of a function's signature: the first line from its start
+/// that opens its body
+fn signature_end(lines: &[&str], body: &RangeInclusive<usize>) -> usize {
+    let last = (*body.start() + MAX_SIGNATURE_LINES - 1).min(*body.end());
+    (*body.start()..=last)
+        .find(|&n| {
+            let text = lines[n - 1].trim_end();
+            text.contains('{') || text.ends_with(':') || text.ends_with("=>")
+        })
+        .unwrap_or(*body.start())
+}
+
+/// For each name in `used`, the last line in `range` that defines it
+fn definitions(
+    lines: &[&str],
+    used: &HashSet<&str>,
+    range: std::ops::Range<usize>,
+) -> BTreeSet<usize> {
+    used.iter()
+        .filter_map(|name| {
+            let name = regex::escape(name);
+            let definition = Regex::new(&format!(
+                r"\b(?:let|const|var|val|auto)\s+(?:mut\s+)?{name}\b|\b{name}\s*(?::[^=]*)?:?=(?:[^=]|$)|\bfor\s+\(?\s*(?:(?:let|const|var)\s+)?{name}\b|\bas\s+{name}\b"
+            ))
+            .ok()?;
+            range
+                .clone()
+                .rev()
+                .find(|&n| definition.is_match(lines[n - 1]))
+        })
+        .collect()
+}
@@ -0,0 +1,492 @@
+//! AI enrichment requests: which findings are sent, the prompts they are sent
+//! in, and what those requests are expected to cost.
+//!
+//! `revet review --ai` and its `--estimate-only` dry run (and the node
+//! binding's `aiEstimate`) all build their requests with [`plan`], so an
+//! estimate is always made from the prompts a real run would send.
+//!
+//! When `max_cost_per_run` can't cover every eligible finding, findings are
+//! sent in priority order and the rest are skipped for budget. Each part of
+//! the score is weighted under `[ai.prioritization]`:
+//!
+//! | Part | Default | Factor |
+//! |------|---------|--------|
+//! | Severity | 40 | `1` for errors, `0.5` for warnings |
+//! | Confidence | 20 | `1` high, `0.5` medium, `0` low |
+//! | Critical path | 20 | `1` under a `[risk] critical_paths` glob |
+//! | Dependents | 10 | `min(1, log2(1 + d) / 8)` — `d` = callers of the enclosing symbol |
+//! | New | 10 | `1` when the baseline doesn't record the finding |
+//!
+//! Ties are broken by file, line, column and id, so a given set of findings
+//! always enriches the same ones.
+//!
+//! The source sent with each finding is assembled by [`context`].
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AIConfig, AiPrioritizationConfig};
+use crate::graph::{CodeGraph, EdgeKind, NodeId, NodeKind};
+use crate::{Baseline, Confidence, Finding, SecretIndex, Severity};
+
+pub mod context;
+
+pub use context::ContextBuilder;
+
+pub const SYSTEM_PROMPT: &str = r#"You are a senior code reviewer analyzing static analysis findings.
+For each finding, provide a concise explanation of the problem and specific remediation advice.
+Each finding's "context" is source from its file: ">>>" marks the finding line, "..." marks skipped lines.
+
+Respond with a JSON array where each element has:
+- "id": the finding id (copy exactly from input)
+- "note": your explanation and remediation advice in plain text (max 250 chars)
+- "false_positive": true if this is very likely a false positive, false otherwise
+
+Output only a valid JSON array. No markdown fences, no extra text."#;
+
+/// Expected response length per finding (one `note` of at most 250 chars)
+const OUTPUT_TOKENS_PER_FINDING: usize = 80;
+
+/// What the model sees of one finding — its [`context`], never the whole
+/// file
+#[derive(Serialize)]
+struct FindingContext {
+    id: String,
+    severity: String,
+    message: String,
+    file: String,
+    line: usize,
+    context: String,
+}
+
+/// One LLM request: a group of findings and the prompts that describe them
+#[derive(Debug, Clone, PartialEq)]
+pub struct Batch {
+    /// Indices into the findings the batch was built from
+    pub findings: Vec<usize>,
+    pub system: &'static str,
+    pub user: String,
+}
+
+impl Batch {
+    /// Estimated `(input, output)` tokens for this request
+    pub fn estimated_tokens(&self) -> (usize, usize) {
+        (
+            estimate_tokens(self.system) + estimate_tokens(&self.user),
+            self.findings.len() * OUTPUT_TOKENS_PER_FINDING,
+        )
+    }
+}
+
+/// Whether a finding is sent for enrichment: warnings and errors without a
+/// suggestion (findings with suggestions are already self-explanatory)
+pub fn is_eligible(finding: &Finding) -> bool {
+    matches!(finding.severity, Severity::Warning | Severity::Error) && finding.suggestion.is_none()
+}
+
+/// `log2(1 + 255)` — dependents at which the dependents part saturates
+const DEPENDENTS_LOG_CAP: f64 = 8.0;
+
+/// What scoring knows beyond the findings themselves
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PriorityContext<'a> {
+    /// Resolves the symbol enclosing each finding to count its dependents
+    pub graph: Option<&'a CodeGraph>,
+    /// Findings it records are not new; without one every finding is
+    pub baseline: Option<&'a Baseline>,
+    /// `[risk] critical_paths` globs, relative to the repo root
+    pub critical_paths: &'a [String],
+}
+
+/// An eligible finding and its priority
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scored {
+    /// Index into the findings that were scored
+    pub index: usize,
+    pub score: f64,
+}
+
+/// Score one finding, rounded to two decimals (see the module docs)
+pub fn score(
+    finding: &Finding,
+    critical: bool,
+    dependents: usize,
+    new: bool,
+    weights: &AiPrioritizationConfig,
+) -> f64 {
+    let severity = match finding.severity {
+        Severity::Error => 1.0,
+        Severity::Warning => 0.5,
+        Severity::Info => 0.0,
+    };
+    let confidence = match finding.confidence {
+        Confidence::High => 1.0,
+        Confidence::Medium => 0.5,
+        Confidence::Low => 0.0,
+    };
+    let dependents = ((1.0 + dependents as f64).log2() / DEPENDENTS_LOG_CAP).min(1.0);
+    let flag = |on: bool| if on { 1.0 } else { 0.0 };
+    let total = weights.severity * severity
+        + weights.confidence * confidence
+        + weights.critical_path * flag(critical)
+        + weights.dependents * dependents
+        + weights.new * flag(new);
+    (total * 100.0).round() / 100.0
+}
+
+/// Eligible findings that pass `only_new` and `min_severity`, highest
+/// score first
+pub fn prioritize(
+    findings: &[Finding],
+    repo_root: &Path,
+    weights: &AiPrioritizationConfig,
+    context: &PriorityContext,
+) -> Vec<Scored> {
+    let known = match context.baseline {
+        Some(baseline) => baseline.known(findings, repo_root),
+        None => vec![false; findings.len()],
+    };
+    let errors_only = weights.min_severity == "error";
+    let globs: Vec<glob::Pattern> = context
+        .critical_paths
+        .iter()
+        .filter_map(|g| glob::Pattern::new(g).ok())
+        .collect();
+    let symbols = context.graph.map(|g| SymbolIndex::new(g, repo_root));
+
+    let mut scored: Vec<Scored> = findings
+        .iter()
+        .enumerate()
+        .filter(|(i, f)| {
+            is_eligible(f)
+                && !(weights.only_new && known[*i])
+                && !(errors_only && f.severity != Severity::Error)
+        })
+        .map(|(i, f)| {
+            let rel = f.file.strip_prefix(repo_root).unwrap_or(&f.file);
+            let rel = rel.to_string_lossy().replace('\\', "/");
+            let critical = globs.iter().any(|g| g.matches(&rel));
+            let resolved = symbols
+                .as_ref()
+                .map_or(0, |s| s.dependents(&absolute(repo_root, &f.file), f.line));
+            let dependents = f.affected_dependents.max(resolved);
+            Scored {
+                index: i,
+                score: score(f, critical, dependents, !known[i], weights),
+            }
+        })
+        .collect();
+
+    scored.sort_by(|a, b| {
+        let (fa, fb) = (&findings[a.index], &findings[b.index]);
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| fa.file.cmp(&fb.file))
+            .then_with(|| fa.line.cmp(&fb.line))
+            .then_with(|| fa.column.cmp(&fb.column))
+            .then_with(|| fa.id.cmp(&fb.id))
+    });
+    scored
+}
+
+fn absolute(repo_root: &Path, file: &Path) -> PathBuf {
+    if file.is_absolute() {
+        file.to_path_buf()
+    } else {
+        repo_root.join(file)
+    }
+}
+
+/// Symbols by file, for finding the one enclosing a line
+pub(crate) struct SymbolIndex<'a> {
+    graph: &'a CodeGraph,
+    by_file: HashMap<PathBuf, Vec<(usize, usize, NodeId)>>,
+}
+
+impl<'a> SymbolIndex<'a> {
+    pub(crate) fn new(graph: &'a CodeGraph, repo_root: &Path) -> Self {
+        let mut by_file: HashMap<PathBuf, Vec<(usize, usize, NodeId)>> = HashMap::new();
+        for (id, node) in graph.nodes() {
+            if matches!(
+                node.kind(),
+                NodeKind::File | NodeKind::Module | NodeKind::Import
+            ) {
+                continue;
+            }
+            let end = node.end_line().unwrap_or(node.line());
+            by_file
+                .entry(absolute(repo_root, node.file_path()))
+                .or_default()
+                .push((node.line(), end, id));
+        }
+        Self { graph, by_file }
+    }
+
+    /// The innermost symbol of `kind` (any kind when `None`) around `line`
+    /// of `file`, an absolute path
+    pub(crate) fn innermost(
+        &self,
+        file: &Path,
+        line: usize,
+        kind: Option<NodeKind>,
+    ) -> Option<NodeId> {
+        self.by_file
+            .get(file)?
+            .iter()
+            .filter(|(start, end, _)| (*start..=*end).contains(&line))
+            .filter(|(_, _, id)| {
+                kind.is_none_or(|kind| self.graph.node(*id).is_some_and(|n| *n.kind() == kind))
+            })
+            .min_by_key(|(start, end, id)| (end - start, *id))
+            .map(|&(_, _, id)| id)
+    }
+
+    pub(crate) fn graph(&self) -> &'a CodeGraph {
+        self.graph
+    }
+
+    /// Distinct callers and referrers of the innermost symbol around `line`
+    fn dependents(&self, file: &Path, line: usize) -> usize {
+        let Some(id) = self.innermost(file, line, None) else {
+            return 0;
+        };
+        self.graph
+            .edges_to(id)
+            .into_iter()
+            .filter(|(_, edge)| matches!(edge.kind(), EdgeKind::Calls | EdgeKind::References))
+            .map(|(source, _)| source)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+}
+
+/// The requests a run makes and the findings the budget left out
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Plan {
+    pub batches: Vec<Batch>,
+    /// Eligible findings skipped for budget, highest score first
+    pub skipped: Vec<usize>,
+    /// Lowest score that was sent, when findings were skipped for budget
+    pub score_cutoff: Option<f64>,
+}
+
+/// Build the requests for the highest-priority eligible findings whose
+/// estimated cost fits `max_cost`; empty when none are eligible.
+///
+/// All selected findings go in a single request. Secrets are always masked
+/// before leaving the machine, even with `--no-redact`.
+pub fn plan(
+    findings: &[Finding],
+    repo_root: &Path,
+    config: &AIConfig,
+    max_cost: f64,
+    context: &PriorityContext,
+) -> Result<Plan> {
+    let scored = prioritize(findings, repo_root, &config.prioritization, context);
+    if scored.is_empty() {
+        return Ok(Plan::default());
+    }
+
+    let secrets = SecretIndex::from_findings(findings);
+    let builder = ContextBuilder::new(repo_root, &secrets, context.graph)
+        .with_max_tokens(config.context_tokens);
+    let contexts: Vec<FindingContext> = scored
+        .iter()
+        .map(|s| {
+            let f = &findings[s.index];
+            FindingContext {
+                id: f.id.clone(),
+                severity: f.severity.to_string(),
+                message: f.redacted_message().to_string(),
+                file: f.file.to_string_lossy().to_string(),
+                line: f.line,
+                context: builder.build(f),
+            }
+        })
+        .collect();
+    let cost = |user: &str, count: usize| {
+        estimate_cost_usd(
+            &config.provider,
+            &config.model,
+            estimate_tokens(SYSTEM_PROMPT) + estimate_tokens(user),
+            count * OUTPUT_TOKENS_PER_FINDING,
+        )
+    };
+
+    // Cost grows with every finding added, so binary search for the
+    // longest prefix of the ranking that fits
+    let (mut fits, mut over) = (0, scored.len() + 1);
+    while over - fits > 1 {
+        let mid = (fits + over) / 2;
+        if cost(&serde_json::to_string_pretty(&contexts[..mid])?, mid) <= max_cost {
+            fits = mid;
+        } else {
+            over = mid;
+        }
+    }
+
+    let skipped: Vec<usize> = scored[fits..].iter().map(|s| s.index).collect();
+    let score_cutoff = (fits > 0 && !skipped.is_empty()).then(|| scored[fits - 1].score);
+    let batches = if fits == 0 {
+        Vec::new()
+    } else {
+        vec![Batch {
+            findings: scored[..fits].iter().map(|s| s.index).collect(),
+            system: SYSTEM_PROMPT,
+            user: serde_json::to_string_pretty(&contexts[..fits])?,
+        }]
+    };
+    Ok(Plan {
+        batches,
+        skipped,
+        score_cutoff,
+    })
+}
+
+/// The context of a finding without a graph to find its function in: the
+/// lines around it, with any secret spans recorded on findings in the same
+/// file masked.
+pub fn read_snippet(repo_root: &Path, finding: &Finding, secrets: &SecretIndex) -> String {
+    ContextBuilder::new(repo_root, secrets, None).build(finding)
+}
+
+// ── Cost ─────────────────────────────────────────────────────────
+
+/// Rough token estimate: ~4 chars per token
+pub fn estimate_tokens(text: &str) -> usize {
+    (text.len() / 4).max(1)
+}
+
+/// Estimate cost in USD based on provider/model pricing (per 1M tokens)
+pub fn estimate_cost_usd(
+    provider: &str,
+    model: &str,
+    input_tokens: usize,
+    output_tokens: usize,
+) -> f64 {
+    let (input_price, output_price) = match provider {
+        "ollama" => return 0.0, // local inference, always free
+        "openai" => {
+            if model.contains("gpt-4o-mini") {
+                (0.15, 0.60)
+            } else {
+                (2.50, 10.00) // gpt-4o
+            }
+        }
+        _ => {
+            // anthropic
+            if model.contains("opus") {
+                (15.00, 75.00)
+            } else if model.contains("haiku") {
+                (0.80, 4.00)
+            } else {
+                (3.00, 15.00) // sonnet default
+            }
+        }
+    };
+
+    (input_tokens as f64 * input_price + output_tokens as f64 * output_price) / 1_000_000.0
+}
+
+/// Estimated tokens for one request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchEstimate {
+    pub findings: usize,
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+}
+
+/// What the whole run would cost on one provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderEstimate {
+    pub provider: String,
+    pub model: String,
+    pub cost_usd: f64,
+}
+
+/// The requests an `--ai` run would make and what they would cost
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AiEstimate {
+    /// Findings that pass eligibility and the `[ai.prioritization]` filters,
+    /// including those skipped for budget
+    pub eligible: usize,
+    pub requests: usize,
+    pub batches: Vec<BatchEstimate>,
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+    /// Cost on each provider the run may use, in the order they are tried.
+    /// Revet has no fallback providers, so this is the configured one.
+    pub providers: Vec<ProviderEstimate>,
+    /// Cost on the first provider, checked against `max_cost`
+    pub estimated_cost: f64,
+    pub max_cost: f64,
+    /// Whether `max_cost` could not cover every eligible finding
+    pub exceeds_max_cost: bool,
+    /// Eligible findings left out to stay within `max_cost`
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub skipped_budget: usize,
+    /// Lowest score that was sent, when findings were skipped for budget
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_cutoff: Option<f64>,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+impl AiEstimate {
+    pub fn new(plan: &Plan, config: &AIConfig, max_cost: f64) -> Self {
+        let batches: Vec<BatchEstimate> = plan
+            .batches
+            .iter()
+            .map(|b| {
+                let (input_tokens, output_tokens) = b.estimated_tokens();
+                BatchEstimate {
+                    findings: b.findings.len(),
+                    input_tokens,
+                    output_tokens,
+                }
+            })
+            .collect();
+        let input_tokens = batches.iter().map(|b| b.input_tokens).sum();
+        let output_tokens = batches.iter().map(|b| b.output_tokens).sum();
+        let providers = vec![ProviderEstimate {
+            provider: config.provider.clone(),
+            model: config.model.clone(),
+            cost_usd: estimate_cost_usd(
+                &config.provider,
+                &config.model,
+                input_tokens,
+                output_tokens,
+            ),
+        }];
+        let estimated_cost = providers[0].cost_usd;
+
+        Self {
+            eligible: batches.iter().map(|b| b.findings).sum::<usize>() + plan.skipped.len(),
+            requests: batches.len(),
+            batches,
+            input_tokens,
+            output_tokens,
+            providers,
+            estimated_cost,
+            max_cost,
+            exceeds_max_cost: !plan.skipped.is_empty(),
+            skipped_budget: plan.skipped.len(),
+            score_cutoff: plan.score_cutoff,
+        }
+    }
+}
+
+/// The `ai` block of a review: the estimate, and what the run actually
+/// cost (absent for `--estimate-only`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AiReport {
+    #[serde(flatten)]
+    pub estimate: AiEstimate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual_cost: Option<f64>,
+}
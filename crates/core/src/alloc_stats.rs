@@ -0,0 +1,80 @@
+//! Allocation accounting for instrumented builds (`alloc-stats` feature)
+//!
+//! A binary that installs [`CountingAllocator`] as its global allocator
+//! gets the peak allocation of each analyzer batch in `--timings=full`.
+//! Counts are kept per thread: a batch is charged for what its own thread
+//! allocates, so work it hands to other threads is missed and memory freed
+//! on another thread than it was allocated on skews the figures. The peak
+//! is an approximation, good enough to spot the analyzer that balloons.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static CURRENT: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+/// The system allocator, counting live bytes per thread
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC: revet_core::alloc_stats::CountingAllocator =
+///     revet_core::alloc_stats::CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+fn charge(bytes: i64) {
+    // `try_with`: allocations during thread teardown go uncounted
+    let _ = CURRENT.try_with(|current| {
+        let now = current.get() + bytes;
+        current.set(now);
+        let _ = PEAK.try_with(|peak| {
+            if now > peak.get() {
+                peak.set(now);
+            }
+        });
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            charge(layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            charge(layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        charge(-(layout.size() as i64));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            charge(new_size as i64 - layout.size() as i64);
+        }
+        new
+    }
+}
+
+/// Run `f`, returning its result and the most bytes it had allocated on
+/// this thread at any point. Always 0 unless [`CountingAllocator`] is the
+/// global allocator.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, u64) {
+    let start = CURRENT.with(Cell::get);
+    let outer_peak = PEAK.with(|peak| peak.replace(start));
+    let out = f();
+    let peak = PEAK.with(|peak| peak.replace(outer_peak.max(peak.get())));
+    (out, (peak - start).max(0) as u64)
+}
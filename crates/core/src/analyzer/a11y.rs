@@ -0,0 +1,422 @@
+//! Accessibility analyzer — statically decidable a11y mistakes in JSX and
+//! HTML templates
+//!
+//! JSX in `.jsx`, `.tsx` and `.js` files is read with tree-sitter (see
+//! [`jsx`]); HTML files and the templates of Vue, Svelte, Handlebars, ERB,
+//! Jinja and friends with a lightweight tokenizer (see [`html`]). Both yield
+//! the same [`Element`]s, which every sub-rule inspects:
+//!
+//! - `img_alt`: an `<img>` without `alt`. Images hidden with
+//!   `aria-hidden="true"` or `role="presentation"` are exempt
+//! - `click_events`: a click handler on a non-interactive element (`<div>`,
+//!   `<span>`, `<a>` without `href`, ...) with neither a keyboard handler
+//!   nor a `role`. Medium confidence
+//! - `label`: an `<input>`, `<select>` or `<textarea>` with no label: not
+//!   inside a `<label>`, not named by a `for`/`htmlFor` in the same file and
+//!   without `aria-label`, `aria-labelledby` or `title`. Medium confidence
+//! - `html_lang`: an `<html>` element without `lang`
+//! - `tabindex`: a positive `tabindex`, which reorders keyboard focus
+//!
+//! An element with a spread (`{...props}`, `v-bind="attrs"`) or a template
+//! interpolation among its attributes may get the missing attribute from
+//! it, so only `tabindex` is checked on it. Only lowercase (intrinsic)
+//! elements are checked; components are left to their own markup.
+//!
+//! Finding prefix: `A11Y-`
+
+pub mod html;
+pub mod jsx;
+
+use crate::analyzer::{catalog_finding, Analyzer};
+use crate::config::RevetConfig;
+use crate::finding::{Confidence, Finding, Severity};
+use crate::msg;
+use crate::trace::{self, Decision};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Module name of the accessibility analyzer
+pub const MODULE: &str = "a11y";
+
+/// Sub-rules of the accessibility analyzer
+pub const RULES: &[&str] = &["img_alt", "click_events", "label", "html_lang", "tabindex"];
+
+/// Elements a click handler is expected on
+const INTERACTIVE: &[&str] = &[
+    "button", "input", "select", "textarea", "option", "summary", "details", "label",
+];
+
+/// `<input>` types that need no label
+const UNLABELLED_INPUTS: &[&str] = &["hidden", "submit", "button", "reset", "image"];
+
+/// Markup an element was written in, which decides how suggested
+/// attributes are spelled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Markup {
+    Jsx,
+    Html,
+}
+
+/// An attribute value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// Known text: a quoted value, a literal in braces, `true` for a bare
+    /// JSX attribute and `""` for a bare HTML one
+    Literal(String),
+    /// Computed at runtime
+    Expression(String),
+}
+
+/// One attribute of an element
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute {
+    /// Name with framework binding syntax removed (`:alt` → `alt`,
+    /// `@click` → `onclick`)
+    pub name: String,
+    pub value: Value,
+    /// The attribute as written
+    pub text: String,
+}
+
+/// An opening tag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    pub tag: String,
+    pub attributes: Vec<Attribute>,
+    /// Whether a spread or template interpolation may add attributes
+    pub spread: bool,
+    /// Whether the element is inside a `<label>`
+    pub in_label: bool,
+    pub line: usize,
+    /// 1-based character columns of `<tag` on `line`
+    pub column: usize,
+    pub end_column: usize,
+}
+
+impl Element {
+    /// The attribute called `name`, compared case-insensitively
+    pub fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes
+            .iter()
+            .find(|a| a.name.eq_ignore_ascii_case(name))
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.attribute(name).is_some()
+    }
+
+    /// The literal value of `name`, trimmed and lowercased
+    fn literal(&self, name: &str) -> Option<String> {
+        match &self.attribute(name)?.value {
+            Value::Literal(text) => Some(text.trim().to_ascii_lowercase()),
+            Value::Expression(_) => None,
+        }
+    }
+
+    /// HTML and SVG elements, as opposed to components and custom elements
+    fn is_intrinsic(&self) -> bool {
+        self.tag.starts_with(|c: char| c.is_ascii_lowercase())
+            && self.tag.chars().all(|c| c.is_ascii_alphanumeric())
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.literal("aria-hidden").as_deref() == Some("true")
+            || matches!(
+                self.literal("role").as_deref(),
+                Some("presentation" | "none")
+            )
+    }
+}
+
+/// Whether `tag` labels the fields inside it: `<label>` or a component
+/// library's `<FormLabel>`-style wrapper
+pub fn is_label(tag: &str) -> bool {
+    tag == "label" || tag.ends_with("Label")
+}
+
+/// Markup of `path`, when it is scanned
+pub fn markup(path: &Path) -> Option<Markup> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    if jsx::EXTENSIONS.contains(&ext.as_str()) {
+        Some(Markup::Jsx)
+    } else if html::EXTENSIONS.contains(&ext.as_str()) {
+        Some(Markup::Html)
+    } else {
+        None
+    }
+}
+
+/// Analyzer that reports accessibility mistakes in markup
+pub struct A11yAnalyzer;
+
+impl A11yAnalyzer {
+    /// Create an accessibility analyzer
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn scan_source(&self, path: &Path, content: &str) -> Vec<Finding> {
+        let Some(markup) = markup(path) else {
+            return Vec::new();
+        };
+        let elements = match markup {
+            Markup::Jsx => jsx::elements(path, content),
+            Markup::Html => html::elements(content),
+        };
+        check(path, &elements, markup)
+    }
+}
+
+impl Default for A11yAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Findings for the elements of one file
+fn check(path: &Path, elements: &[Element], markup: Markup) -> Vec<Finding> {
+    // `for` / `htmlFor` values; `None` when one of them is computed
+    let mut label_ids = Some(HashSet::new());
+    for element in elements.iter().filter(|e| is_label(&e.tag)) {
+        let target = element
+            .attribute("htmlFor")
+            .or_else(|| element.attribute("for"));
+        match target.map(|a| &a.value) {
+            Some(Value::Literal(id)) => {
+                if let Some(ids) = &mut label_ids {
+                    ids.insert(id.trim().to_string());
+                }
+            }
+            Some(Value::Expression(_)) => {
+                trace::record(path, element.line, Decision::Skipped, || {
+                    "computed label target: fields with an id are not checked".to_string()
+                });
+                label_ids = None;
+            }
+            None => {}
+        }
+    }
+
+    let mut findings = Vec::new();
+    for element in elements {
+        findings.extend(positive_tabindex(path, element, markup));
+        if !element.is_intrinsic() {
+            trace::record(path, element.line, Decision::Skipped, || {
+                format!("<{}> is a component, not a DOM element", element.tag)
+            });
+            continue;
+        }
+        if element.spread {
+            trace::record(path, element.line, Decision::Skipped, || {
+                format!(
+                    "<{}> spreads attributes that may add the missing ones",
+                    element.tag
+                )
+            });
+            continue;
+        }
+        let tag = element.tag.as_str();
+        if tag == "img" && !element.has("alt") && !element.is_hidden() {
+            findings.push(finding(
+                path,
+                element,
+                "img_alt",
+                Confidence::High,
+                msg!("a11y-img-alt", tag = tag),
+                msg!("a11y-img-alt.suggestion"),
+            ));
+        }
+        if tag == "html" && element.literal("lang").is_none_or(|l| l.is_empty()) {
+            let computed = matches!(
+                element.attribute("lang").map(|a| &a.value),
+                Some(Value::Expression(_))
+            );
+            if !computed {
+                findings.push(finding(
+                    path,
+                    element,
+                    "html_lang",
+                    Confidence::High,
+                    msg!("a11y-html-lang"),
+                    msg!("a11y-html-lang.suggestion"),
+                ));
+            }
+        }
+        if has_unreachable_click(element) {
+            let attributes = match markup {
+                Markup::Jsx => "role=\"button\" tabIndex={0} onKeyDown={…}",
+                Markup::Html => "role=\"button\" tabindex=\"0\" onkeydown=\"…\"",
+            };
+            findings.push(finding(
+                path,
+                element,
+                "click_events",
+                Confidence::Medium,
+                msg!("a11y-click-events", tag = tag),
+                msg!("a11y-click-events.suggestion", attributes = attributes),
+            ));
+        }
+        if is_unlabelled_field(element, label_ids.as_ref()) {
+            let id = match element.attribute("id").map(|a| &a.value) {
+                Some(Value::Literal(id)) if !id.trim().is_empty() => id.trim().to_string(),
+                _ => "…".to_string(),
+            };
+            let label = match markup {
+                Markup::Jsx => format!("<label htmlFor=\"{}\">", id),
+                Markup::Html => format!("<label for=\"{}\">", id),
+            };
+            findings.push(finding(
+                path,
+                element,
+                "label",
+                Confidence::Medium,
+                msg!("a11y-label", tag = tag),
+                msg!("a11y-label.suggestion", label = label),
+            ));
+        }
+    }
+    findings
+}
+
+/// A positive `tabindex`, also checked on elements with a spread
+fn positive_tabindex(path: &Path, element: &Element, markup: Markup) -> Option<Finding> {
+    let attribute = element.attribute("tabindex")?;
+    let value = match &attribute.value {
+        Value::Literal(text) | Value::Expression(text) => text.trim(),
+    };
+    let value = value.trim_matches(|c| c == '"' || c == '\'');
+    if value.parse::<i64>().ok()? <= 0 {
+        return None;
+    }
+    let fix = match markup {
+        Markup::Jsx => format!("{}={{0}}", attribute.name),
+        Markup::Html => format!("{}=\"0\"", attribute.name),
+    };
+    Some(finding(
+        path,
+        element,
+        "tabindex",
+        Confidence::High,
+        msg!(
+            "a11y-tabindex",
+            attribute = attribute.text.as_str(),
+            tag = element.tag.as_str()
+        ),
+        msg!("a11y-tabindex.suggestion", fix = fix),
+    ))
+}
+
+/// A click handler keyboard users cannot trigger: on a non-interactive
+/// element, with neither a key handler nor a role
+fn has_unreachable_click(element: &Element) -> bool {
+    if !element.has("onclick") || element.has("role") {
+        return false;
+    }
+    let interactive = INTERACTIVE.contains(&element.tag.as_str())
+        || (matches!(element.tag.as_str(), "a" | "area") && element.has("href"));
+    let keyboard = ["onkeydown", "onkeyup", "onkeypress"]
+        .iter()
+        .any(|name| element.has(name));
+    !interactive && !keyboard
+}
+
+/// A form field without a label. `label_ids` is `None` when a label's
+/// target is computed, so any field with an id may be its target
+fn is_unlabelled_field(element: &Element, label_ids: Option<&HashSet<String>>) -> bool {
+    match element.tag.as_str() {
+        "select" | "textarea" => {}
+        "input" => match &element.attribute("type").map(|a| &a.value) {
+            Some(Value::Expression(_)) => return false,
+            Some(Value::Literal(kind))
+                if UNLABELLED_INPUTS.contains(&kind.trim().to_ascii_lowercase().as_str()) =>
+            {
+                return false
+            }
+            _ => {}
+        },
+        _ => return false,
+    }
+    if element.in_label
+        || ["aria-label", "aria-labelledby", "title"]
+            .iter()
+            .any(|name| element.has(name))
+    {
+        return false;
+    }
+    match &element.attribute("id").map(|a| &a.value) {
+        None => true,
+        Some(Value::Expression(_)) => false,
+        Some(Value::Literal(id)) => label_ids.is_some_and(|ids| !ids.contains(id.trim())),
+    }
+}
+
+fn finding(
+    path: &Path,
+    element: &Element,
+    rule: &str,
+    confidence: Confidence,
+    message: crate::i18n::Msg,
+    suggestion: crate::i18n::Msg,
+) -> Finding {
+    Finding {
+        rule: Some(rule.to_string()),
+        confidence,
+        column: Some(element.column),
+        end_column: Some(element.end_column),
+        ..catalog_finding(
+            Severity::Warning,
+            message,
+            path.to_path_buf(),
+            element.line,
+            Some(suggestion),
+            None,
+        )
+    }
+}
+
+impl Analyzer for A11yAnalyzer {
+    fn name(&self) -> &str {
+        "Accessibility"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "A11Y"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.a11y
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some(MODULE)
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        markup(path).is_some()
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
+        files
+            .iter()
+            .filter(|f| self.accepts(f))
+            .filter_map(|f| Some(self.scan_source(f, &std::fs::read_to_string(f).ok()?)))
+            .flatten()
+            .collect()
+    }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        self.scan_source(path, content)
+    }
+
+    fn extra_extensions(&self) -> &[&str] {
+        html::DOT_EXTENSIONS
+    }
+}
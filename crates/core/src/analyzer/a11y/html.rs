@@ -0,0 +1,340 @@
+//! Opening tags of HTML files and templates, from a lightweight tokenizer
+//!
+//! Comments, doctypes and the bodies of `<script>` and `<style>` are
+//! skipped; everything else that looks like `<tag attr="value" ...>` is an
+//! [`Element`]. Framework binding syntax is folded into plain attributes:
+//! `:alt`, `v-bind:alt`, `[alt]`, `[attr.alt]` and Svelte's `alt={x}` and
+//! `{alt}` become a computed `alt`; `@click`, `v-on:click`, `(click)` and
+//! `on:click` become `onclick`. `v-bind="attrs"`, `{...props}`,
+//! `...attributes` and any template interpolation between attributes
+//! (`{{ attrs }}`, `<%= %>`, `{% if %}`) set [`Element::spread`].
+
+use super::{is_label, Attribute, Element, Value};
+
+/// Extensions whose markup is checked
+pub const EXTENSIONS: &[&str] = &[
+    "html",
+    "htm",
+    "xhtml",
+    "vue",
+    "svelte",
+    "hbs",
+    "handlebars",
+    "erb",
+    "ejs",
+    "njk",
+    "jinja",
+    "jinja2",
+    "j2",
+    "twig",
+    "liquid",
+];
+
+/// [`EXTENSIONS`] with a leading dot, for discovery
+pub const DOT_EXTENSIONS: &[&str] = &[
+    ".html",
+    ".htm",
+    ".xhtml",
+    ".vue",
+    ".svelte",
+    ".hbs",
+    ".handlebars",
+    ".erb",
+    ".ejs",
+    ".njk",
+    ".jinja",
+    ".jinja2",
+    ".j2",
+    ".twig",
+    ".liquid",
+];
+
+/// Elements whose content is not markup
+const RAW_TEXT: &[&str] = &["script", "style"];
+
+/// Every opening tag of `content`, in source order
+pub fn elements(content: &str) -> Vec<Element> {
+    let mut tokenizer = Tokenizer {
+        source: content,
+        pos: 0,
+    };
+    let mut elements = Vec::new();
+    let mut labels = 0usize;
+
+    while let Some(offset) = content[tokenizer.pos..].find('<') {
+        tokenizer.pos += offset;
+        let rest = tokenizer.rest();
+        if rest.starts_with("<!--") {
+            tokenizer.skip_past("-->");
+        } else if rest.starts_with("<!") || rest.starts_with("<?") || rest.starts_with("<%") {
+            tokenizer.skip_past(">");
+        } else if let Some(closing) = rest.strip_prefix("</") {
+            if is_label(&tag_name(closing)) {
+                labels = labels.saturating_sub(1);
+            }
+            tokenizer.skip_past(">");
+        } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let (element, self_closing) = tokenizer.element(labels > 0);
+            let tag = element.tag.to_ascii_lowercase();
+            if !self_closing {
+                if is_label(&element.tag) {
+                    labels += 1;
+                }
+                if RAW_TEXT.contains(&tag.as_str()) {
+                    tokenizer.skip_raw_text(&tag);
+                }
+            }
+            elements.push(element);
+        } else {
+            tokenizer.pos += 1;
+        }
+    }
+    elements
+}
+
+/// The tag name `text` starts with. All-uppercase names (`<IMG>`) are
+/// lowercased; mixed-case ones are components and kept as written.
+fn tag_name(text: &str) -> String {
+    let name: String = text
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+        .collect();
+    if name.chars().any(|c| c.is_ascii_lowercase()) {
+        name
+    } else {
+        name.to_ascii_lowercase()
+    }
+}
+
+struct Tokenizer<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn skip_past(&mut self, end: &str) {
+        self.pos = match self.rest().find(end) {
+            Some(i) => self.pos + i + end.len(),
+            None => self.source.len(),
+        };
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skip to the end tag of raw-text element `tag`
+    fn skip_raw_text(&mut self, tag: &str) {
+        let end = format!("</{}", tag);
+        let lower = self.rest().to_ascii_lowercase();
+        self.pos = match lower.find(&end) {
+            Some(i) => self.pos + i,
+            None => self.source.len(),
+        };
+    }
+
+    /// 1-based line and character column of byte offset `pos`
+    fn position(&self, pos: usize) -> (usize, usize) {
+        let before = &self.source[..pos];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line = before.matches('\n').count() + 1;
+        (line, before[line_start..].chars().count() + 1)
+    }
+
+    /// The opening tag at `pos`, and whether it closes itself (`/>`)
+    fn element(&mut self, in_label: bool) -> (Element, bool) {
+        let start = self.pos;
+        let tag = tag_name(&self.rest()[1..]);
+        self.pos += 1 + tag.len();
+        let (line, column) = self.position(start);
+        let mut element = Element {
+            end_column: column + 1 + tag.chars().count(),
+            tag,
+            attributes: Vec::new(),
+            spread: false,
+            in_label,
+            line,
+            column,
+        };
+
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.is_empty() {
+                return (element, false);
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                return (element, false);
+            }
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return (element, true);
+            }
+            if let Some(end) = interpolation_end(rest) {
+                // `{{ attrs }}`, `<%= %>`, `{% %}`: may add any attribute
+                self.pos += end;
+                element.spread = true;
+                continue;
+            }
+            if rest.starts_with('{') {
+                // Svelte: `{...props}` or the shorthand `{alt}`
+                let end = matching_brace(rest);
+                let inner = rest[1..(end - 1).max(1)].trim();
+                self.pos += end;
+                match inner.strip_prefix("...") {
+                    Some(_) => element.spread = true,
+                    None if !inner.is_empty() => element.attributes.push(Attribute {
+                        name: inner.to_string(),
+                        value: Value::Expression(inner.to_string()),
+                        text: rest[..end].to_string(),
+                    }),
+                    None => {}
+                }
+                continue;
+            }
+            self.attribute(&mut element);
+        }
+    }
+
+    fn attribute(&mut self, element: &mut Element) {
+        let start = self.pos;
+        let rest = self.rest();
+        let name_len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '"' | '\''))
+            .unwrap_or(rest.len());
+        let name_len = match name_len {
+            // A stray `=` or quote
+            0 => {
+                self.pos += rest.chars().next().map_or(1, char::len_utf8);
+                return;
+            }
+            // `disabled/>`
+            n if rest[..n].ends_with('/') && rest[n..].starts_with('>') => n - 1,
+            n => n,
+        };
+        let name = &rest[..name_len];
+        self.pos += name_len;
+        if name == "/" {
+            return;
+        }
+
+        let after = self.rest();
+        let trimmed = after.trim_start();
+        let value = if trimmed.starts_with('=') {
+            self.pos += after.len() - trimmed.len() + 1;
+            self.skip_whitespace();
+            Some(self.value())
+        } else {
+            None
+        };
+        let text = self.source[start..self.pos].to_string();
+        match fold(name, value) {
+            Some((name, value)) => element.attributes.push(Attribute { name, value, text }),
+            None => element.spread = true,
+        }
+    }
+
+    /// An attribute value: quoted, in braces, or unquoted
+    fn value(&mut self) -> Value {
+        let rest = self.rest();
+        if let Some(quote) = rest.chars().next().filter(|c| matches!(c, '"' | '\'')) {
+            let end = rest[1..].find(quote).map_or(rest.len(), |i| i + 2);
+            self.pos += end;
+            return Value::Literal(rest[1..end.saturating_sub(1).max(1)].to_string());
+        }
+        if rest.starts_with('{') {
+            let end = matching_brace(rest);
+            self.pos += end;
+            return Value::Expression(rest[1..end.saturating_sub(1).max(1)].to_string());
+        }
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '>')
+            .unwrap_or(rest.len());
+        let end = if rest[..end].ends_with('/') && rest[end..].starts_with('>') {
+            end - 1
+        } else {
+            end
+        };
+        self.pos += end;
+        Value::Literal(rest[..end].to_string())
+    }
+}
+
+/// Length of a template interpolation at the start of `text`
+fn interpolation_end(text: &str) -> Option<usize> {
+    let close = if text.starts_with("{{") {
+        "}}"
+    } else if text.starts_with("{%") {
+        "%}"
+    } else if text.starts_with("<%") {
+        "%>"
+    } else {
+        return None;
+    };
+    Some(
+        text[2..]
+            .find(close)
+            .map_or(text.len(), |i| i + 2 + close.len()),
+    )
+}
+
+/// Length of the braced text at the start of `text`, braces included
+fn matching_brace(text: &str) -> usize {
+    let mut depth = 0usize;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// Fold framework binding syntax into a plain attribute; `None` for an
+/// attribute spread
+fn fold(name: &str, value: Option<Value>) -> Option<(String, Value)> {
+    let computed = |value: Option<Value>| match value {
+        Some(Value::Literal(text)) | Some(Value::Expression(text)) => Value::Expression(text),
+        None => Value::Expression(String::new()),
+    };
+    // `v-bind="attrs"`, Ember's `...attributes`
+    if name == "v-bind" || name.starts_with("...") {
+        return None;
+    }
+    let bound = name
+        .strip_prefix("v-bind:")
+        .or_else(|| name.strip_prefix(':'))
+        .or_else(|| {
+            name.strip_prefix("[attr.")
+                .and_then(|n| n.strip_suffix(']'))
+        })
+        .or_else(|| name.strip_prefix('[').and_then(|n| n.strip_suffix(']')))
+        .or_else(|| name.strip_prefix("bind:"));
+    if let Some(bound) = bound {
+        return Some((bound.to_string(), computed(value)));
+    }
+    let event = name
+        .strip_prefix("v-on:")
+        .or_else(|| name.strip_prefix('@'))
+        .or_else(|| name.strip_prefix('(').and_then(|n| n.strip_suffix(')')))
+        .or_else(|| name.strip_prefix("on:"));
+    if let Some(event) = event {
+        // Drop modifiers: `@click.prevent`, `on:click|preventDefault`
+        let event = event.split(['.', '|']).next().unwrap_or(event);
+        return Some((format!("on{}", event), computed(value)));
+    }
+    let value = value.unwrap_or_else(|| Value::Literal(String::new()));
+    Some((name.to_string(), value))
+}
@@ -0,0 +1,145 @@
+//! JSX elements, read with tree-sitter
+//!
+//! Every `jsx_opening_element` and `jsx_self_closing_element` becomes an
+//! [`Element`]. Values in braces that are string, number or boolean
+//! literals count as literals (`tabIndex={2}`); anything else is an
+//! expression. A `{...props}` spread sets [`Element::spread`].
+
+use super::{is_label, Attribute, Element, Value};
+use crate::parser::grammar::language_for;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Extensions whose JSX is checked
+pub const EXTENSIONS: &[&str] = &["jsx", "tsx", "js"];
+
+/// Every element of `content`, in source order
+pub fn elements(path: &Path, content: &str) -> Vec<Element> {
+    let Some(tree) = language_for(path).and_then(|g| g.parse(content)) else {
+        return Vec::new();
+    };
+    let mut scanner = Scanner {
+        source: content,
+        elements: Vec::new(),
+    };
+    scanner.walk(tree.root_node(), false);
+    scanner.elements
+}
+
+struct Scanner<'a> {
+    source: &'a str,
+    elements: Vec<Element>,
+}
+
+impl<'a> Scanner<'a> {
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.source.as_bytes()).unwrap_or("")
+    }
+
+    fn walk(&mut self, node: Node, outer: bool) {
+        let mut in_label = outer;
+        match node.kind() {
+            "jsx_element" => {
+                if let Some(open) = node.child_by_field_name("open_tag") {
+                    if let Some(element) = self.element(open, in_label) {
+                        in_label |= is_label(&element.tag);
+                        self.elements.push(element);
+                    }
+                }
+            }
+            "jsx_self_closing_element" => {
+                if let Some(element) = self.element(node, in_label) {
+                    self.elements.push(element);
+                }
+            }
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            // Elements in the label's own attributes are not inside it
+            let inside = in_label && child.kind() != "jsx_opening_element";
+            self.walk(child, inside || outer);
+        }
+    }
+
+    fn element(&self, node: Node, in_label: bool) -> Option<Element> {
+        let name = node.child_by_field_name("name")?;
+        let start = node.start_position();
+        let line = self.source.lines().nth(start.row).unwrap_or("");
+        let mut element = Element {
+            tag: self.text(name).to_string(),
+            attributes: Vec::new(),
+            spread: false,
+            in_label,
+            line: start.row + 1,
+            column: char_column(line, start.column),
+            end_column: char_column(line, name.end_position().column),
+        };
+
+        let mut cursor = node.walk();
+        for attribute in node.named_children(&mut cursor) {
+            match attribute.kind() {
+                "jsx_attribute" => {
+                    if let Some(attribute) = self.attribute(attribute) {
+                        element.attributes.push(attribute);
+                    }
+                }
+                // `{...props}`
+                "jsx_expression" => element.spread = true,
+                _ => {}
+            }
+        }
+        Some(element)
+    }
+
+    fn attribute(&self, node: Node) -> Option<Attribute> {
+        let name = self.text(node.named_child(0)?).to_string();
+        let value = match node.named_child(1) {
+            None => Value::Literal("true".to_string()),
+            Some(value) => self.value(value),
+        };
+        Some(Attribute {
+            name,
+            value,
+            text: self.text(node).to_string(),
+        })
+    }
+
+    fn value(&self, node: Node) -> Value {
+        match node.kind() {
+            "string" => Value::Literal(unquote(self.text(node)).to_string()),
+            "jsx_expression" => match node.named_child(0) {
+                Some(inner) => match inner.kind() {
+                    "string" | "number" | "true" | "false" => self.value(inner),
+                    "template_string" if !has_child(inner, "template_substitution") => {
+                        Value::Literal(unquote(self.text(inner)).to_string())
+                    }
+                    _ => Value::Expression(self.text(inner).to_string()),
+                },
+                None => Value::Expression(String::new()),
+            },
+            "number" | "true" | "false" => Value::Literal(self.text(node).to_string()),
+            _ => Value::Expression(self.text(node).to_string()),
+        }
+    }
+}
+
+fn has_child(node: Node, kind: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node.named_children(&mut cursor).any(|c| c.kind() == kind);
+    found
+}
+
+/// 1-based character column of byte offset `byte` in `line`
+fn char_column(line: &str, byte: usize) -> usize {
+    line.get(..byte).map_or(byte, |s| s.chars().count()) + 1
+}
+
+fn unquote(text: &str) -> &str {
+    for quote in ["\"", "'", "`"] {
+        if let Some(inner) = text.strip_prefix(quote).and_then(|t| t.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    text
+}
@@ -3,22 +3,37 @@
 //! Scans `.js`, `.ts`, `.jsx`, `.tsx`, and `.py` files line-by-line for patterns
 //! that cause unhandled rejections, silent failures, and race conditions.
 //! Only one finding per line (first matching pattern wins) to reduce noise.
+//!
+//! JavaScript/TypeScript findings follow the file's language targets (see
+//! [`crate::language_target`]): suggestions for ECMAScript targets before
+//! ES2017 don't propose `async`/`await`, and an unhandled `.then()` chain is
+//! info rather than a warning for Node.js before 15, which logs unhandled
+//! rejections instead of exiting.
 
 use crate::analyzer::{make_finding, Analyzer};
-use crate::config::RevetConfig;
+use crate::config::{RevetConfig, RuleSelection};
 use crate::finding::{Finding, FixKind, Severity};
+use crate::language_target::{Language, LanguageTargets};
 use regex::Regex;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 /// A compiled async pattern detection rule
 struct AsyncPattern {
+    /// Sub-rule id (`async_patterns:<rule>`)
+    rule: &'static str,
     name: &'static str,
     regex: Regex,
     severity: Severity,
     /// If set, skip the match when the line contains this substring
     reject_if_contains: Option<&'static str>,
     suggestion: &'static str,
+    /// Suggestion for ECMAScript targets before this edition, which lack
+    /// the syntax `suggestion` proposes
+    before_edition: Option<(&'static str, &'static str)>,
+    /// Severity for Node.js targets before this release
+    before_node: Option<(&'static str, Severity)>,
     fix_kind: FixKind,
 }
 
@@ -29,69 +44,93 @@ fn patterns() -> &'static [AsyncPattern] {
         vec![
             // --- Error: Always wrong ---
             AsyncPattern {
+                rule: "async_executor",
                 name: "Async Promise executor",
                 regex: Regex::new(r"new\s+Promise\s*\(\s*async").unwrap(),
                 severity: Severity::Error,
                 reject_if_contains: None,
                 suggestion: "Remove async from Promise executor; use resolve/reject callbacks instead",
+                before_edition: None,
+                before_node: None,
                 fix_kind: FixKind::Suggestion,
             },
             AsyncPattern {
+                rule: "foreach_await",
                 name: "Await in forEach",
                 regex: Regex::new(r"\.forEach\s*\(\s*async").unwrap(),
                 severity: Severity::Error,
                 reject_if_contains: None,
                 suggestion: "Use for...of loop or Promise.all(items.map(...)) instead of forEach with async",
+                before_edition: None,
+                before_node: None,
                 fix_kind: FixKind::Suggestion,
             },
             // --- Warning: Usually problematic ---
             AsyncPattern {
+                rule: "unhandled_then",
                 name: "Unhandled .then() chain",
                 regex: Regex::new(r"\.then\s*\(").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: Some(".catch"),
                 suggestion: "Add .catch() handler or use async/await with try/catch",
+                before_edition: Some(("2017", "Add a .catch() handler to the chain")),
+                before_node: Some(("15", Severity::Info)),
                 fix_kind: FixKind::Suggestion,
             },
             AsyncPattern {
+                rule: "map_without_all",
                 name: "Async map without Promise.all",
                 regex: Regex::new(r"\.map\s*\(\s*async").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: Some("Promise.all"),
                 suggestion: "Wrap with await Promise.all(...) to collect async map results",
+                before_edition: None,
+                before_node: None,
                 fix_kind: FixKind::Suggestion,
             },
             AsyncPattern {
+                rule: "async_timer",
                 name: "Async timer callback",
                 regex: Regex::new(r"(?:setTimeout|setInterval)\s*\(\s*async").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: None,
                 suggestion: "Extract async logic and add error handling inside the callback",
+                before_edition: None,
+                before_node: None,
                 fix_kind: FixKind::Suggestion,
             },
             AsyncPattern {
+                rule: "unawaited",
                 name: "Floating Python coroutine",
                 regex: Regex::new(r"asyncio\.(?:sleep|gather|wait_for|create_task|ensure_future)\s*\(").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: Some("await"),
                 suggestion: "Add await before asyncio call",
+                before_edition: None,
+                before_node: None,
                 fix_kind: FixKind::Suggestion,
             },
             // --- Info: Code smell / style ---
             AsyncPattern {
+                rule: "swallowed_catch",
                 name: "Swallowed error in catch",
                 regex: Regex::new(r"\.catch\s*\([^{]*\{\s*\}\s*\)").unwrap(),
                 severity: Severity::Info,
                 reject_if_contains: None,
                 suggestion: "Handle or log the error instead of swallowing it",
+                before_edition: None,
+                before_node: None,
                 fix_kind: FixKind::Suggestion,
             },
             AsyncPattern {
+                rule: "return_await",
                 name: "Redundant return await",
                 regex: Regex::new(r"return\s+await\s+").unwrap(),
                 severity: Severity::Info,
                 reject_if_contains: None,
                 suggestion: "Remove await from return statement (unless inside try/catch)",
+                before_edition: None,
+                before_node: None,
                 fix_kind: FixKind::Suggestion,
             },
         ]
@@ -101,12 +140,42 @@ fn patterns() -> &'static [AsyncPattern] {
 /// File extensions to scan for async patterns
 const ASYNC_EXTENSIONS: &[&str] = &["js", "ts", "jsx", "tsx", "py"];
 
+/// Sub-rule ids, in pattern order
+const RULES: &[&str] = &[
+    "async_executor",
+    "foreach_await",
+    "unhandled_then",
+    "map_without_all",
+    "async_timer",
+    "unawaited",
+    "swallowed_catch",
+    "return_await",
+];
+
 /// Analyzer that detects async/await anti-patterns
-pub struct AsyncPatternsAnalyzer;
+pub struct AsyncPatternsAnalyzer {
+    rules: RuleSelection,
+    /// `[targets]`
+    targets: BTreeMap<String, String>,
+}
 
 impl AsyncPatternsAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self::with_rules(&RuleSelection::default())
+    }
+
+    /// Create an analyzer that only tries the sub-rules `rules` selects
+    pub fn with_rules(rules: &RuleSelection) -> Self {
+        Self {
+            rules: rules.clone(),
+            targets: BTreeMap::new(),
+        }
+    }
+
+    /// Take the language targets `[targets]` sets over manifests
+    pub fn with_targets(mut self, targets: &BTreeMap<String, String>) -> Self {
+        self.targets = targets.clone();
+        self
     }
 
     /// Check if a file should be scanned based on its extension
@@ -127,12 +196,20 @@ impl AsyncPatternsAnalyzer {
     }
 
     /// Scan a single file for async pattern issues
-    fn scan_file(path: &Path) -> Vec<Finding> {
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return Vec::new(),
-        };
+    fn scan_file(&self, path: &Path, targets: &mut LanguageTargets) -> Vec<Finding> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => self.scan_source(path, &content, targets),
+            Err(_) => Vec::new(),
+        }
+    }
 
+    fn scan_source(
+        &self,
+        path: &Path,
+        content: &str,
+        targets: &mut LanguageTargets,
+    ) -> Vec<Finding> {
+        let is_python = path.extension().is_some_and(|e| e == "py");
         let all_patterns = patterns();
         let mut findings = Vec::new();
 
@@ -143,6 +220,9 @@ impl AsyncPatternsAnalyzer {
 
             // First matching pattern wins for this line
             for pat in all_patterns {
+                if !self.rules.allows(pat.rule) {
+                    continue;
+                }
                 if pat.regex.is_match(line) {
                     // Check reject filter
                     if let Some(reject) = pat.reject_if_contains {
@@ -151,14 +231,35 @@ impl AsyncPatternsAnalyzer {
                         }
                     }
 
-                    findings.push(make_finding(
-                        pat.severity,
-                        pat.name.to_string(),
-                        path.to_path_buf(),
-                        line_num + 1,
-                        Some(pat.suggestion.to_string()),
-                        Some(pat.fix_kind.clone()),
-                    ));
+                    let mut severity = pat.severity;
+                    let mut suggestion = pat.suggestion;
+                    if !is_python {
+                        if let Some((edition, legacy)) = pat.before_edition {
+                            if !targets
+                                .for_file(Language::EcmaScript, path)
+                                .at_least(edition)
+                            {
+                                suggestion = legacy;
+                            }
+                        }
+                        if let Some((release, lower)) = pat.before_node {
+                            if !targets.for_file(Language::Node, path).at_least(release) {
+                                severity = lower;
+                            }
+                        }
+                    }
+
+                    findings.push(Finding {
+                        rule: Some(pat.rule.to_string()),
+                        ..make_finding(
+                            severity,
+                            pat.name.to_string(),
+                            path.to_path_buf(),
+                            line_num + 1,
+                            Some(suggestion.to_string()),
+                            Some(pat.fix_kind.clone()),
+                        )
+                    });
                     break;
                 }
             }
@@ -187,16 +288,41 @@ impl Analyzer for AsyncPatternsAnalyzer {
         config.modules.async_patterns
     }
 
-    fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
+    fn module(&self) -> Option<&'static str> {
+        Some("async_patterns")
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Self::should_scan(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], repo_root: &Path) -> Vec<Finding> {
+        let mut targets = LanguageTargets::new(repo_root, &self.targets);
         let mut findings = Vec::new();
 
         for file in files {
             if !Self::should_scan(file) {
                 continue;
             }
-            findings.extend(Self::scan_file(file));
+            findings.extend(self.scan_file(file, &mut targets));
         }
 
         findings
     }
+
+    fn analyze_source(&self, path: &Path, content: &str, repo_root: &Path) -> Vec<Finding> {
+        if !Self::should_scan(path) {
+            return Vec::new();
+        }
+        let mut targets = LanguageTargets::new(repo_root, &self.targets);
+        self.scan_source(path, content, &mut targets)
+    }
 }
@@ -2,11 +2,14 @@
 //!
 //! Performs DFS cycle detection over `Imports` edges between `File` nodes.
 //! Each unique cycle is reported once (canonicalized by rotating to the smallest NodeId).
+//! Cycles made only of type-only imports (`import type`, `if TYPE_CHECKING:`)
+//! are erased at runtime and are not reported.
 
-use crate::analyzer::GraphAnalyzer;
+use crate::analyzer::{catalog_finding, GraphAnalyzer};
 use crate::config::RevetConfig;
 use crate::finding::{Finding, Severity};
-use crate::graph::{CodeGraph, EdgeKind, NodeId, NodeKind};
+use crate::graph::{CodeGraph, EdgeKind, ImportKind, NodeId, NodeKind};
+use crate::msg;
 use std::collections::{HashMap, HashSet};
 
 pub struct CircularImportsAnalyzer;
@@ -110,6 +113,18 @@ fn dfs(
     color.insert(node, Color::Black);
 }
 
+/// Whether every step of `cycle`, including the one back to its start, is
+/// only type-only imports
+fn is_type_only(graph: &CodeGraph, cycle: &[NodeId]) -> bool {
+    let next = cycle.iter().cycle().skip(1);
+    cycle.iter().zip(next).all(|(&from, &to)| {
+        graph
+            .edges_from(from)
+            .filter(|(target, edge)| *target == to && matches!(edge.kind(), EdgeKind::Imports))
+            .all(|(_, edge)| edge.import_kind() == ImportKind::TypeOnly)
+    })
+}
+
 /// Rotate cycle to start at the smallest NodeId for deduplication.
 fn canonicalize_cycle(mut cycle: Vec<NodeId>) -> Vec<NodeId> {
     if cycle.is_empty() {
@@ -138,12 +153,16 @@ impl GraphAnalyzer for CircularImportsAnalyzer {
         config.modules.cycles
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("cycles")
+    }
+
     fn analyze_graph(&self, graph: &CodeGraph, _config: &RevetConfig) -> Vec<Finding> {
         let cycles = find_import_cycles(graph);
         let mut findings = Vec::new();
 
         for cycle in cycles {
-            if cycle.is_empty() {
+            if cycle.is_empty() || is_type_only(graph, &cycle) {
                 continue;
             }
 
@@ -173,19 +192,14 @@ impl GraphAnalyzer for CircularImportsAnalyzer {
                 format!("{} → {}", cycle_path[0], cycle_path[0])
             };
 
-            findings.push(Finding {
-                id: String::new(), // renumbered by dispatcher
-                severity: Severity::Warning,
-                message: format!("Circular import detected: {}", cycle_str),
-                file: node.file_path().clone(),
-                line: node.line(),
-                affected_dependents: 0,
-                suggestion: Some(
-                    "Break the cycle by extracting shared code to a separate module".to_string(),
-                ),
-                fix_kind: None,
-                ..Default::default()
-            });
+            findings.push(catalog_finding(
+                Severity::Warning,
+                msg!("circular-imports-cycle", cycle = cycle_str),
+                node.file_path().clone(),
+                node.line(),
+                Some(msg!("circular-imports-cycle.suggestion")),
+                None,
+            ));
         }
 
         findings
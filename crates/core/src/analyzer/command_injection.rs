@@ -156,17 +156,19 @@ impl CommandInjectionAnalyzer {
     }
 
     fn scan_file(path: &Path) -> Vec<Finding> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::scan_source(path, &content),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn scan_source(path: &Path, content: &str) -> Vec<Finding> {
         let ext = path
             .extension()
             .and_then(|e| e.to_str())
             .unwrap_or("")
             .to_lowercase();
 
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return Vec::new(),
-        };
-
         let all_patterns = patterns();
         let mut findings = Vec::new();
 
@@ -177,14 +179,17 @@ impl CommandInjectionAnalyzer {
                     continue;
                 }
                 if pat.regex.is_match(line) {
-                    findings.push(make_finding(
-                        pat.severity,
-                        format!("Possible command injection: {}", pat.name),
-                        path.to_path_buf(),
-                        line_num + 1,
-                        Some(pat.suggestion.to_string()),
-                        Some(FixKind::Suggestion),
-                    ));
+                    findings.push(Finding {
+                        rule: Some("command_injection".to_string()),
+                        ..make_finding(
+                            pat.severity,
+                            format!("Possible command injection: {}", pat.name),
+                            path.to_path_buf(),
+                            line_num + 1,
+                            Some(pat.suggestion.to_string()),
+                            Some(FixKind::Suggestion),
+                        )
+                    });
                     break; // One finding per line
                 }
             }
@@ -213,10 +218,26 @@ impl Analyzer for CommandInjectionAnalyzer {
         config.modules.security
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("security")
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        &["command_injection"]
+    }
+
     fn extra_extensions(&self) -> &[&str] {
         EXTRA_EXTENSIONS
     }
 
+    fn accepts(&self, path: &Path) -> bool {
+        Self::should_scan(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
     fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
         let mut findings = Vec::new();
         for file in files {
@@ -227,4 +248,11 @@ impl Analyzer for CommandInjectionAnalyzer {
         }
         findings
     }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        if !Self::should_scan(path) {
+            return Vec::new();
+        }
+        Self::scan_source(path, content)
+    }
 }
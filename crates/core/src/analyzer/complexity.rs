@@ -5,13 +5,23 @@
 //! - **Parameters**: argument count (from graph `NodeData::Function`)
 //! - **Cyclomatic complexity**: branch-counting heuristic on the function body
 //! - **Nesting depth**: max brace/indentation depth within the function body
+//!
+//! Go build-constrained variants of one function are reported once, with a
+//! note listing the platforms.
 
-use crate::analyzer::GraphAnalyzer;
+use crate::analyzer::{catalog_finding, GraphAnalyzer};
+use crate::calibrate;
 use crate::config::RevetConfig;
 use crate::finding::{Finding, Severity};
-use crate::graph::{CodeGraph, NodeData, NodeKind};
+use crate::graph::{CodeGraph, Node, NodeData, NodeId, NodeKind};
+use crate::msg;
+use crate::parser::go_build::{self, Platform, PlatformVariants};
+use crate::trace::{self, Decision};
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+pub mod delta;
 
 // ── Thresholds ────────────────────────────────────────────────────────────────
 
@@ -219,6 +229,319 @@ fn python_max_nesting(lines: &[&str]) -> usize {
     max_extra
 }
 
+/// Per-function check results, tagged with the metric for variant dedupe.
+type TaggedFinding = (NodeId, &'static str, Finding);
+
+/// The four per-function metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Metric {
+    Length,
+    Parameters,
+    Cyclomatic,
+    Nesting,
+}
+
+impl Metric {
+    /// Tag used to dedupe platform variants
+    fn tag(self) -> &'static str {
+        match self {
+            Metric::Length => "length",
+            Metric::Parameters => "parameters",
+            Metric::Cyclomatic => "cyclomatic",
+            Metric::Nesting => "nesting",
+        }
+    }
+}
+
+/// One metric of one function, with the thresholds it is checked against
+#[derive(Debug, Clone, Copy)]
+struct Measurement {
+    metric: Metric,
+    value: usize,
+    warn: usize,
+    error: usize,
+}
+
+impl Measurement {
+    fn severity(&self) -> Option<Severity> {
+        if self.value >= self.error {
+            Some(Severity::Error)
+        } else if self.value >= self.warn {
+            Some(Severity::Warning)
+        } else {
+            None
+        }
+    }
+
+    /// The threshold `value` reaches: the error one, else the warning one
+    fn threshold(&self) -> usize {
+        if self.value >= self.error {
+            self.error
+        } else {
+            self.warn
+        }
+    }
+
+    /// The finding for this measurement against the absolute thresholds
+    fn finding(&self, node: &Node) -> Option<Finding> {
+        let severity = self.severity()?;
+        let error = severity == Severity::Error;
+        let (name, value, max) = (node.name(), self.value, self.threshold());
+        let (message, suggestion) = match (self.metric, error) {
+            (Metric::Length, true) => (
+                msg!(
+                    "complexity-length-error",
+                    name = name,
+                    lines = value,
+                    max = max
+                ),
+                msg!("complexity-length-error.suggestion"),
+            ),
+            (Metric::Length, false) => (
+                msg!(
+                    "complexity-length-warning",
+                    name = name,
+                    lines = value,
+                    max = max
+                ),
+                msg!("complexity-length-warning.suggestion"),
+            ),
+            (Metric::Parameters, true) => (
+                msg!(
+                    "complexity-parameters-error",
+                    name = name,
+                    count = value,
+                    max = max
+                ),
+                msg!("complexity-parameters-error.suggestion"),
+            ),
+            (Metric::Parameters, false) => (
+                msg!(
+                    "complexity-parameters-warning",
+                    name = name,
+                    count = value,
+                    max = max
+                ),
+                msg!("complexity-parameters-warning.suggestion"),
+            ),
+            (Metric::Cyclomatic, true) => (
+                msg!(
+                    "complexity-cyclomatic-error",
+                    name = name,
+                    score = value,
+                    max = max
+                ),
+                msg!("complexity-cyclomatic-error.suggestion"),
+            ),
+            (Metric::Cyclomatic, false) => (
+                msg!(
+                    "complexity-cyclomatic-warning",
+                    name = name,
+                    score = value,
+                    max = max
+                ),
+                msg!("complexity-cyclomatic-warning.suggestion"),
+            ),
+            (Metric::Nesting, true) => (
+                msg!(
+                    "complexity-nesting-error",
+                    name = name,
+                    depth = value,
+                    max = max
+                ),
+                msg!("complexity-nesting-error.suggestion"),
+            ),
+            (Metric::Nesting, false) => (
+                msg!(
+                    "complexity-nesting-warning",
+                    name = name,
+                    depth = value,
+                    max = max
+                ),
+                msg!("complexity-nesting-warning.suggestion"),
+            ),
+        };
+        Some(catalog_finding(
+            severity,
+            message,
+            node.file_path().clone(),
+            node.line(),
+            Some(suggestion),
+            None,
+        ))
+    }
+}
+
+/// Measure all four metrics of one function node.
+///
+/// `content` is the full source of the node's file; the content-based metrics
+/// (cyclomatic complexity, nesting) are skipped when it is unavailable.
+/// Non-function nodes and inactive platform variants have no measurements.
+fn measure(
+    node: &Node,
+    content: Option<&str>,
+    complexity_warn: usize,
+    platforms: &[Platform],
+) -> Vec<Measurement> {
+    let mut measurements = Vec::new();
+    if !matches!(node.kind(), NodeKind::Function) {
+        return measurements;
+    }
+    if !go_build::is_active(node, platforms) {
+        return measurements;
+    }
+
+    let NodeData::Function { parameters, .. } = node.data() else {
+        return measurements;
+    };
+
+    let file_path = node.file_path();
+    let start_line = node.line();
+    let end_line = node.end_line().unwrap_or(start_line);
+    let lang = lang_from_path(file_path);
+
+    // ── 1. Function length ──────────────────────────────────────────
+    measurements.push(Measurement {
+        metric: Metric::Length,
+        value: end_line.saturating_sub(start_line),
+        warn: FN_LEN_WARN,
+        error: FN_LEN_ERROR,
+    });
+
+    // ── 2. Parameter count ──────────────────────────────────────────
+    measurements.push(Measurement {
+        metric: Metric::Parameters,
+        value: parameters.len(),
+        warn: PARAM_WARN,
+        error: PARAM_ERROR,
+    });
+
+    // ── 3 & 4. Cyclomatic complexity + nesting (require file content) ──
+    if start_line == 0 || end_line < start_line {
+        return measurements;
+    }
+
+    let Some(content) = content else {
+        return measurements;
+    };
+
+    let all_lines: Vec<&str> = content.lines().collect();
+    let line_count = all_lines.len();
+
+    // tree-sitter lines are 0-indexed; graph stores 1-based line numbers
+    let start_idx = start_line.saturating_sub(1);
+    let end_idx = end_line.min(line_count);
+
+    if start_idx >= end_idx {
+        return measurements;
+    }
+
+    let fn_lines = &all_lines[start_idx..end_idx];
+
+    measurements.push(Measurement {
+        metric: Metric::Cyclomatic,
+        value: cyclomatic_complexity(fn_lines, lang),
+        warn: complexity_warn,
+        error: complexity_warn * 2,
+    });
+    measurements.push(Measurement {
+        metric: Metric::Nesting,
+        value: max_nesting_depth(fn_lines, lang),
+        warn: NESTING_WARN,
+        error: NESTING_ERROR,
+    });
+    measurements
+}
+
+/// Run all four metrics on one function node against the absolute
+/// thresholds.
+fn check_function(
+    node_id: NodeId,
+    node: &Node,
+    content: Option<&str>,
+    complexity_warn: usize,
+    platforms: &[Platform],
+    findings: &mut Vec<TaggedFinding>,
+) {
+    for m in measure(node, content, complexity_warn, platforms) {
+        if let Some(finding) = m.finding(node) {
+            trace::record(node.file_path(), node.line(), Decision::Matched, || {
+                format!(
+                    "`{}`: {} {} reaches {}",
+                    node.name(),
+                    m.metric.tag(),
+                    m.value,
+                    m.threshold()
+                )
+            });
+            findings.push((node_id, m.metric.tag(), finding));
+        }
+    }
+}
+
+/// Cyclomatic complexity of one function and the lines it spans
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionComplexity {
+    pub file: PathBuf,
+    pub line: usize,
+    pub end_line: usize,
+    pub complexity: usize,
+}
+
+/// Cyclomatic complexity of every function in the files `wanted` accepts.
+/// Files whose source can't be read are left out.
+pub fn function_complexity(
+    graph: &CodeGraph,
+    config: &RevetConfig,
+    wanted: impl Fn(&Path) -> bool,
+) -> Vec<FunctionComplexity> {
+    let platforms = go_build::parse_platforms(&config.parser.go.platforms);
+    let mut contents: HashMap<&Path, Option<String>> = HashMap::new();
+    let mut functions = Vec::new();
+
+    for (_, node) in graph.nodes() {
+        if !matches!(node.kind(), NodeKind::Function) || !go_build::is_active(node, &platforms) {
+            continue;
+        }
+        let file_path = node.file_path();
+        if !wanted(file_path) {
+            continue;
+        }
+        let Some(content) = contents
+            .entry(file_path.as_path())
+            .or_insert_with(|| fs::read_to_string(file_path).ok())
+        else {
+            continue;
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        let start_idx = node.line().saturating_sub(1);
+        let end_idx = node.end_line().unwrap_or(node.line()).min(lines.len());
+        if start_idx >= end_idx {
+            continue;
+        }
+        functions.push(FunctionComplexity {
+            file: file_path.clone(),
+            line: node.line(),
+            end_line: end_idx,
+            complexity: cyclomatic_complexity(
+                &lines[start_idx..end_idx],
+                lang_from_path(file_path),
+            ),
+        });
+    }
+    functions
+}
+
+/// Aggregate complexity per file: the sum of the cyclomatic complexity of
+/// every function in it. Files whose source can't be read are left out.
+pub fn file_complexity(graph: &CodeGraph, config: &RevetConfig) -> HashMap<PathBuf, usize> {
+    let mut totals: HashMap<PathBuf, usize> = HashMap::new();
+    for function in function_complexity(graph, config, |_| true) {
+        *totals.entry(function.file).or_default() += function.complexity;
+    }
+    totals
+}
+
 // ── GraphAnalyzer impl ────────────────────────────────────────────────────────
 
 impl GraphAnalyzer for ComplexityAnalyzer {
@@ -234,221 +557,76 @@ impl GraphAnalyzer for ComplexityAnalyzer {
         config.modules.complexity
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("complexity")
+    }
+
     fn analyze_graph(&self, graph: &CodeGraph, config: &RevetConfig) -> Vec<Finding> {
-        let complexity_warn = config.modules.complexity_threshold;
-        let complexity_error = complexity_warn * 2;
+        let platforms = go_build::parse_platforms(&config.parser.go.platforms);
+        let mut contents: HashMap<&Path, Option<String>> = HashMap::new();
         let mut findings = Vec::new();
 
-        for (_, node) in graph.nodes() {
+        for (node_id, node) in graph.nodes() {
             if !matches!(node.kind(), NodeKind::Function) {
                 continue;
             }
+            let content = contents
+                .entry(node.file_path().as_path())
+                .or_insert_with(|| fs::read_to_string(node.file_path()).ok());
+            check_function(
+                node_id,
+                node,
+                content.as_deref(),
+                config.modules.complexity_threshold,
+                &platforms,
+                &mut findings,
+            );
+        }
 
-            let NodeData::Function { parameters, .. } = node.data() else {
-                continue;
-            };
-
-            let file_path = node.file_path();
-            let start_line = node.line();
-            let end_line = node.end_line().unwrap_or(start_line);
-            let fn_length = end_line.saturating_sub(start_line);
-            let param_count = parameters.len();
-            let lang = lang_from_path(file_path);
-
-            // ── 1. Function length ──────────────────────────────────────────
-            if fn_length >= FN_LEN_ERROR {
-                findings.push(Finding {
-                    id: String::new(),
-                    severity: Severity::Error,
-                    message: format!(
-                        "Function `{}` is {} lines long (max recommended: {})",
-                        node.name(),
-                        fn_length,
-                        FN_LEN_ERROR
-                    ),
-                    file: file_path.clone(),
-                    line: start_line,
-                    affected_dependents: 0,
-                    suggestion: Some(
-                        "Break this function into smaller, focused functions".to_string(),
-                    ),
-                    fix_kind: None,
-                    ..Default::default()
-                });
-            } else if fn_length >= FN_LEN_WARN {
-                findings.push(Finding {
-                    id: String::new(),
-                    severity: Severity::Warning,
-                    message: format!(
-                        "Function `{}` is {} lines long (recommended: <{})",
-                        node.name(),
-                        fn_length,
-                        FN_LEN_WARN
-                    ),
-                    file: file_path.clone(),
-                    line: start_line,
-                    affected_dependents: 0,
-                    suggestion: Some(
-                        "Consider breaking this function into smaller, focused functions"
-                            .to_string(),
-                    ),
-                    fix_kind: None,
-                    ..Default::default()
-                });
-            }
-
-            // ── 2. Parameter count ──────────────────────────────────────────
-            if param_count >= PARAM_ERROR {
-                findings.push(Finding {
-                    id: String::new(),
-                    severity: Severity::Error,
-                    message: format!(
-                        "Function `{}` has {} parameters (max recommended: {})",
-                        node.name(),
-                        param_count,
-                        PARAM_ERROR
-                    ),
-                    file: file_path.clone(),
-                    line: start_line,
-                    affected_dependents: 0,
-                    suggestion: Some(
-                        "Group related parameters into a struct or configuration object"
-                            .to_string(),
-                    ),
-                    fix_kind: None,
-                    ..Default::default()
-                });
-            } else if param_count >= PARAM_WARN {
-                findings.push(Finding {
-                    id: String::new(),
-                    severity: Severity::Warning,
-                    message: format!(
-                        "Function `{}` has {} parameters (recommended: <{})",
-                        node.name(),
-                        param_count,
-                        PARAM_WARN
-                    ),
-                    file: file_path.clone(),
-                    line: start_line,
-                    affected_dependents: 0,
-                    suggestion: Some(
-                        "Consider grouping related parameters into a struct or object".to_string(),
-                    ),
-                    fix_kind: None,
-                    ..Default::default()
-                });
-            }
-
-            // ── 3 & 4. Cyclomatic complexity + nesting (require file content) ──
-            if start_line == 0 || end_line < start_line {
-                continue;
-            }
-
-            let Ok(content) = fs::read_to_string(file_path) else {
-                continue;
-            };
-
-            let all_lines: Vec<&str> = content.lines().collect();
-            let line_count = all_lines.len();
-
-            // tree-sitter lines are 0-indexed; graph stores 1-based line numbers
-            let start_idx = start_line.saturating_sub(1);
-            let end_idx = end_line.min(line_count);
-
-            if start_idx >= end_idx {
-                continue;
-            }
-
-            let fn_lines = &all_lines[start_idx..end_idx];
-
-            // Cyclomatic complexity
-            let complexity = cyclomatic_complexity(fn_lines, lang);
-            if complexity >= complexity_error {
-                findings.push(Finding {
-                    id: String::new(),
-                    severity: Severity::Error,
-                    message: format!(
-                        "Function `{}` has cyclomatic complexity of {} (max recommended: {})",
-                        node.name(),
-                        complexity,
-                        complexity_error
-                    ),
-                    file: file_path.clone(),
-                    line: start_line,
-                    affected_dependents: 0,
-                    suggestion: Some(
-                        "Reduce branching by extracting helper functions or simplifying logic"
-                            .to_string(),
-                    ),
-                    fix_kind: None,
-                    ..Default::default()
-                });
-            } else if complexity >= complexity_warn {
-                findings.push(Finding {
-                    id: String::new(),
-                    severity: Severity::Warning,
-                    message: format!(
-                        "Function `{}` has cyclomatic complexity of {} (recommended: <{})",
-                        node.name(),
-                        complexity,
-                        complexity_warn
-                    ),
-                    file: file_path.clone(),
-                    line: start_line,
-                    affected_dependents: 0,
-                    suggestion: Some(
-                        "Consider reducing branching by extracting helper functions".to_string(),
-                    ),
-                    fix_kind: None,
-                    ..Default::default()
-                });
-            }
+        // Platform variants (e.g. `_linux.go` / `_windows.go`) are one logical
+        // function — report each metric once for the whole group
+        PlatformVariants::build(graph).dedupe(findings)
+    }
 
-            // Nesting depth
-            let nesting = max_nesting_depth(fn_lines, lang);
-            if nesting >= NESTING_ERROR {
-                findings.push(Finding {
-                    id: String::new(),
-                    severity: Severity::Error,
-                    message: format!(
-                        "Function `{}` has nesting depth of {} (max recommended: {})",
-                        node.name(),
-                        nesting,
-                        NESTING_ERROR
-                    ),
-                    file: file_path.clone(),
-                    line: start_line,
-                    affected_dependents: 0,
-                    suggestion: Some(
-                        "Reduce nesting using early returns or helper functions".to_string(),
-                    ),
-                    fix_kind: None,
-                    ..Default::default()
-                });
-            } else if nesting >= NESTING_WARN {
-                findings.push(Finding {
-                    id: String::new(),
-                    severity: Severity::Warning,
-                    message: format!(
-                        "Function `{}` has nesting depth of {} (recommended: <{})",
-                        node.name(),
-                        nesting,
-                        NESTING_WARN
-                    ),
-                    file: file_path.clone(),
-                    line: start_line,
-                    affected_dependents: 0,
-                    suggestion: Some(
-                        "Consider reducing nesting using early returns or helper functions"
-                            .to_string(),
-                    ),
-                    fix_kind: None,
-                    ..Default::default()
-                });
+    fn analyze_file(
+        &self,
+        graph: &CodeGraph,
+        file: &Path,
+        content: &str,
+        config: &RevetConfig,
+    ) -> Vec<Finding> {
+        let platforms = go_build::parse_platforms(&config.parser.go.platforms);
+        let mut findings = Vec::new();
+        for (node_id, node) in graph.nodes() {
+            if node.file_path() == file && matches!(node.kind(), NodeKind::Function) {
+                check_function(
+                    node_id,
+                    node,
+                    Some(content),
+                    config.modules.complexity_threshold,
+                    &platforms,
+                    &mut findings,
+                );
             }
         }
+        PlatformVariants::build(graph).dedupe(findings)
+    }
 
-        findings
+    /// Cyclomatic complexity of every function: the one threshold
+    /// (`[modules] complexity_threshold`) that is configurable
+    fn measure(&self, graph: &CodeGraph, config: &RevetConfig) -> Vec<calibrate::Metric> {
+        let values = function_complexity(graph, config, |_| true)
+            .into_iter()
+            .map(|f| f.complexity as f64)
+            .collect();
+        vec![calibrate::Metric::at_least(
+            "modules.complexity_threshold",
+            "cyclomatic complexity per function",
+            0,
+            config.modules.complexity_threshold as f64,
+            1.0,
+            values,
+        )]
     }
 }
 
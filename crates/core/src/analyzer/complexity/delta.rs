@@ -0,0 +1,377 @@
+//! Delta mode — judge a diff by what it does to each function's complexity.
+//!
+//! Absolute thresholds blame whoever touches a legacy function for
+//! complexity they didn't add. In delta mode (`[analyzers.complexity] mode =
+//! "delta"` or `--complexity-delta`, diff-based runs only) a function that
+//! overlaps the changed lines is measured in the base tree too, and a metric
+//! is reported only when the change makes it worse while it is at or above
+//! its threshold, e.g. "cyclomatic complexity 19 → 24 (threshold 15)".
+//!
+//! - Functions the diff doesn't touch report nothing.
+//! - New functions, in added files or without a base counterpart, are
+//!   checked against the absolute thresholds.
+//! - A function's base counterpart is the base function in the same file with
+//!   the same name and parameters; line ranges are ignored, so moved code
+//!   still matches. Same-named functions are paired in file order when both
+//!   trees have as many of them, and otherwise count as new. A function whose
+//!   parameters changed matches by name alone when the name is unique in
+//!   both trees.
+//!
+//! [`DeltaSummary`] records how many findings absolute mode would have
+//! reported, so teams can see what the delta rule defers.
+
+use super::{measure, Measurement, Metric, TaggedFinding};
+use crate::analyzer::catalog_finding;
+use crate::config::RevetConfig;
+use crate::diff::{ChangeType, DiffAnalyzer, DiffFileLines, DiffLineMap, GitTreeReader};
+use crate::finding::Finding;
+use crate::git_objects::{GitObjects, MissingBlob};
+use crate::graph::{CodeGraph, Node, NodeData, NodeKind};
+use crate::msg;
+use crate::parser::go_build::{self, PlatformVariants};
+use crate::parser::ParserDispatcher;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Complexity findings of a delta-mode run, compared with absolute mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DeltaSummary {
+    /// Findings reported: metrics a change made worse, and new functions
+    pub reported: usize,
+    /// Absolute-mode findings the delta rule did not report
+    pub deferred: usize,
+    /// Findings absolute mode would have reported
+    pub absolute: usize,
+}
+
+/// The base side of a diff: the changed lines, and the base-tree version of
+/// every modified file, parsed under its head path
+pub struct DeltaBase {
+    repo_root: PathBuf,
+    changed: DiffLineMap,
+    graph: CodeGraph,
+    sources: HashMap<PathBuf, String>,
+}
+
+impl DeltaBase {
+    /// `changed` and `sources` are keyed by path relative to `repo_root`;
+    /// `sources` holds the base content of modified files.
+    pub fn new(
+        repo_root: &Path,
+        changed: DiffLineMap,
+        sources: HashMap<PathBuf, String>,
+        dispatcher: &ParserDispatcher,
+    ) -> Self {
+        let mut graph = CodeGraph::new(repo_root.to_path_buf());
+        let mut abs_sources = HashMap::new();
+        for (rel, content) in sources {
+            let abs = repo_root.join(&rel);
+            if let Some(parser) = dispatcher.find_parser(&abs) {
+                let _ = parser.parse_source(&content, &abs, &mut graph);
+                abs_sources.insert(abs, content);
+            }
+        }
+        Self {
+            repo_root: repo_root.to_path_buf(),
+            changed,
+            graph,
+            sources: abs_sources,
+        }
+    }
+
+    /// Diff the working tree against `base` and read the base version of
+    /// each modified (or renamed) file from git, through `objects`. A file
+    /// a partial clone lacks the base of keeps absolute thresholds.
+    pub fn at_ref(
+        repo_root: &Path,
+        base: &str,
+        dispatcher: &ParserDispatcher,
+        objects: &GitObjects,
+    ) -> Result<Self> {
+        let analyzer = DiffAnalyzer::new(repo_root)?.with_objects(objects.clone());
+        let diff = analyzer.get_diff(base, None)?;
+        let reader = GitTreeReader::new(repo_root)?.with_objects(objects.clone());
+
+        let mut sources = HashMap::new();
+        for cf in analyzer.get_changed_files(&diff)? {
+            if !matches!(cf.change_type, ChangeType::Modified | ChangeType::Renamed) {
+                continue;
+            }
+            let old_path = cf.old_path.as_deref().unwrap_or(&cf.path);
+            match reader.read_file_at_ref(base, old_path) {
+                Ok(Some(content)) => {
+                    sources.insert(cf.path, content);
+                }
+                Ok(None) => {}
+                Err(e) if e.is::<MissingBlob>() => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let changed = analyzer.get_all_changed_lines(base)?;
+        Ok(Self::new(repo_root, changed, sources, dispatcher))
+    }
+
+    /// Number of modified files read from the base tree
+    pub fn file_count(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Repository the diff belongs to
+    pub fn repo_root(&self) -> &Path {
+        &self.repo_root
+    }
+
+    /// Changed lines of the diff, keyed by path relative to the repository
+    pub fn changed(&self) -> &DiffLineMap {
+        &self.changed
+    }
+
+    /// Base content of each modified file, keyed by absolute head path
+    pub fn sources(&self) -> impl Iterator<Item = (&Path, &str)> {
+        self.sources
+            .iter()
+            .map(|(path, content)| (path.as_path(), content.as_str()))
+    }
+
+    /// Whether the diff changed `line` of `file` (an absolute path)
+    pub fn is_changed(&self, file: &Path, line: usize) -> bool {
+        let rel = file.strip_prefix(&self.repo_root).unwrap_or(file);
+        match self.changed.get(rel) {
+            Some(DiffFileLines::AllNew) => true,
+            Some(DiffFileLines::Lines(lines)) => lines.contains(&line),
+            None => false,
+        }
+    }
+
+    /// How the diff touches `node`
+    fn touch(&self, node: &Node) -> Touch {
+        let rel = node
+            .file_path()
+            .strip_prefix(&self.repo_root)
+            .unwrap_or(node.file_path());
+        match self.changed.get(rel) {
+            None => Touch::Untouched,
+            Some(DiffFileLines::AllNew) => Touch::New,
+            Some(DiffFileLines::Lines(lines)) => {
+                let start = node.line();
+                let end = node.end_line().unwrap_or(start);
+                if lines.iter().any(|l| (start..=end).contains(l)) {
+                    Touch::Modified
+                } else {
+                    Touch::Untouched
+                }
+            }
+        }
+    }
+}
+
+enum Touch {
+    Untouched,
+    New,
+    Modified,
+}
+
+/// What a head function is compared against
+enum Before {
+    /// Outside the diff: nothing is reported
+    Untouched,
+    /// Absolute thresholds apply
+    New,
+    /// Measurements of the base counterpart
+    Base(Vec<Measurement>),
+}
+
+/// Function nodes of one graph, grouped by file and name
+type FunctionIndex<'g> = HashMap<(&'g Path, &'g str), Vec<&'g Node>>;
+
+fn index(graph: &CodeGraph) -> FunctionIndex<'_> {
+    let mut index: FunctionIndex = HashMap::new();
+    for (_, node) in graph.nodes() {
+        if matches!(node.kind(), NodeKind::Function) {
+            index
+                .entry((node.file_path().as_path(), node.name()))
+                .or_default()
+                .push(node);
+        }
+    }
+    for nodes in index.values_mut() {
+        nodes.sort_by_key(|n| n.line());
+    }
+    index
+}
+
+/// Parameter names and types, compared to match functions across versions
+fn signature(node: &Node) -> Vec<(&str, Option<&str>)> {
+    match node.data() {
+        NodeData::Function { parameters, .. } => parameters
+            .iter()
+            .map(|p| (p.name.as_str(), p.param_type.as_deref()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn with_signature<'g>(nodes: &[&'g Node], sig: &[(&str, Option<&str>)]) -> Vec<&'g Node> {
+    nodes
+        .iter()
+        .filter(|n| signature(n) == sig)
+        .copied()
+        .collect()
+}
+
+/// The base version of head function `node`, if it can be told apart
+fn counterpart<'b>(
+    node: &Node,
+    head: &FunctionIndex<'_>,
+    base: &FunctionIndex<'b>,
+) -> Option<&'b Node> {
+    let key = (node.file_path().as_path(), node.name());
+    let base_nodes = base.get(&key)?;
+    let head_nodes = head.get(&key)?;
+
+    let sig = signature(node);
+    let base_same = with_signature(base_nodes, &sig);
+    let head_same = with_signature(head_nodes, &sig);
+
+    if !base_same.is_empty() {
+        if base_same.len() != head_same.len() {
+            return None;
+        }
+        let rank = head_same.iter().position(|n| std::ptr::eq(*n, node))?;
+        return Some(base_same[rank]);
+    }
+    // Parameters changed: match by name when it is unambiguous
+    match (base_nodes.as_slice(), head_nodes.as_slice()) {
+        ([base_node], [_]) => Some(*base_node),
+        _ => None,
+    }
+}
+
+/// The finding for a metric the change made worse
+fn delta_finding(node: &Node, m: &Measurement, from: usize) -> Option<Finding> {
+    let severity = m.severity()?;
+    let (name, to, max) = (node.name(), m.value, m.threshold());
+    let (message, suggestion) = match m.metric {
+        Metric::Length => (
+            msg!(
+                "complexity-length-delta",
+                name = name,
+                from = from,
+                to = to,
+                max = max
+            ),
+            msg!("complexity-length-delta.suggestion"),
+        ),
+        Metric::Parameters => (
+            msg!(
+                "complexity-parameters-delta",
+                name = name,
+                from = from,
+                to = to,
+                max = max
+            ),
+            msg!("complexity-parameters-delta.suggestion"),
+        ),
+        Metric::Cyclomatic => (
+            msg!(
+                "complexity-cyclomatic-delta",
+                name = name,
+                from = from,
+                to = to,
+                max = max
+            ),
+            msg!("complexity-cyclomatic-delta.suggestion"),
+        ),
+        Metric::Nesting => (
+            msg!(
+                "complexity-nesting-delta",
+                name = name,
+                from = from,
+                to = to,
+                max = max
+            ),
+            msg!("complexity-nesting-delta.suggestion"),
+        ),
+    };
+    Some(catalog_finding(
+        severity,
+        message,
+        node.file_path().clone(),
+        node.line(),
+        Some(suggestion),
+        None,
+    ))
+}
+
+/// Run the complexity checks on `head` in delta mode against `base`
+pub fn analyze(
+    head: &CodeGraph,
+    base: &DeltaBase,
+    config: &RevetConfig,
+) -> (Vec<Finding>, DeltaSummary) {
+    let platforms = go_build::parse_platforms(&config.parser.go.platforms);
+    let complexity_warn = config.modules.complexity_threshold;
+    let head_index = index(head);
+    let base_index = index(&base.graph);
+    let mut contents: HashMap<&Path, Option<String>> = HashMap::new();
+    let mut absolute: Vec<TaggedFinding> = Vec::new();
+    let mut reported: Vec<TaggedFinding> = Vec::new();
+
+    for (node_id, node) in head.nodes() {
+        if !matches!(node.kind(), NodeKind::Function) {
+            continue;
+        }
+        let content = contents
+            .entry(node.file_path().as_path())
+            .or_insert_with(|| fs::read_to_string(node.file_path()).ok());
+        let measurements = measure(node, content.as_deref(), complexity_warn, &platforms);
+
+        let before = match base.touch(node) {
+            Touch::Untouched => Before::Untouched,
+            Touch::New => Before::New,
+            Touch::Modified => match counterpart(node, &head_index, &base_index) {
+                Some(base_node) => {
+                    let source = base.sources.get(base_node.file_path()).map(String::as_str);
+                    Before::Base(measure(base_node, source, complexity_warn, &platforms))
+                }
+                None => Before::New,
+            },
+        };
+
+        for m in &measurements {
+            let Some(finding) = m.finding(node) else {
+                continue;
+            };
+            let tag = m.metric.tag();
+            absolute.push((node_id, tag, finding.clone()));
+            let finding = match &before {
+                Before::Untouched => None,
+                Before::New => Some(finding),
+                Before::Base(before) => match before.iter().find(|b| b.metric == m.metric) {
+                    Some(from) if m.value > from.value => delta_finding(node, m, from.value),
+                    Some(_) => None,
+                    // Not measurable in the base tree: judge it as new
+                    None => Some(finding),
+                },
+            };
+            if let Some(finding) = finding {
+                reported.push((node_id, tag, finding));
+            }
+        }
+    }
+
+    let variants = PlatformVariants::build(head);
+    let absolute = variants.dedupe(absolute).len();
+    let findings = variants.dedupe(reported);
+    let summary = DeltaSummary {
+        reported: findings.len(),
+        deferred: absolute.saturating_sub(findings.len()),
+        absolute,
+    };
+    (findings, summary)
+}
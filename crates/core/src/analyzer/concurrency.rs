@@ -0,0 +1,1248 @@
+//! Concurrency analyzer (`RACE`) — shared mutable state without synchronization
+//!
+//! Conservative, per-language checks for the data races that hurt most:
+//!
+//! - **Python** (`shared_state`): module-level variables holding a mutable
+//!   container (`[]`, `{}`, `set()`, `defaultdict(...)`...) and class-level
+//!   ones, mutated — `append`, `x[k] = v`, `del x[k]`, or rebound through
+//!   `global` — in a function that runs concurrently. A function runs
+//!   concurrently when the graph's `Calls` edges reach it from an entry
+//!   point: the target of `threading.Thread(target=...)`, `Timer`, an
+//!   executor `submit`/`map`, `run_in_executor`, `asyncio.to_thread` and the
+//!   like (plus `[analyzers.concurrency] entry_points`), or a route handler.
+//! - **TypeScript/JavaScript** (`handler_state`): module-level objects,
+//!   arrays, `Map`s and `Set`s, and `let`/`var` bindings, written in a
+//!   function reached from a request handler: an Express route (as found
+//!   for the `CONTRACT` analyzer) or an exported Next.js `GET`/`POST`/...
+//!   or `pages/api` default export.
+//! - **Rust**: `static mut` items (`static_mut`) and `unsafe impl Send` /
+//!   `unsafe impl Sync` (`unsafe_send_sync`), flagged outright. `OnceLock`
+//!   is only suggested to crates whose Rust target is 1.70 or later (see
+//!   [`crate::language_target`]).
+//!
+//! Reads never count, nor do writes inside a `with` on something named like
+//! a lock (`with lock:`, `with self._mutex:`) or in a function that calls
+//! `.acquire()` (`mutex.lock()`, `runExclusive` in JavaScript). The
+//! reachability-based findings are `Medium` confidence, the Rust ones
+//! `High`. Test, generated and `[[vendored]]` files are left out.
+
+use super::contract::endpoints::Backend;
+use super::coupling::Scope;
+use super::websec::{descendants, line, text};
+use super::{catalog_finding, GraphAnalyzer};
+use crate::config::{ConcurrencyConfig, RevetConfig};
+use crate::finding::{Confidence, Finding, FindingDetails, Severity};
+use crate::graph::{CodeGraph, EdgeKind, EdgeMetadata, NodeId, NodeKind};
+use crate::language_target::{Language, LanguageTargets};
+use crate::msg;
+use crate::parser::grammar::{language_for, Family};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use tree_sitter::Node;
+
+/// `[modules]` name of the analyzer
+pub const MODULE: &str = "concurrency";
+
+/// Sub-rules, as used in `[analyzers.concurrency]` `only`/`disable` and
+/// `severity`
+pub const RULES: &[&str] = &[
+    "shared_state",
+    "handler_state",
+    "static_mut",
+    "unsafe_send_sync",
+];
+
+/// Calls that run their function argument on another thread
+const ENTRY_POINTS: &[&str] = &[
+    "Thread",
+    "Timer",
+    "submit",
+    "executor.map",
+    "pool.map",
+    "apply_async",
+    "run_in_executor",
+    "to_thread",
+    "start_new_thread",
+];
+
+/// Python calls that mutate their receiver
+const PYTHON_MUTATORS: &[&str] = &[
+    "append",
+    "appendleft",
+    "extend",
+    "extendleft",
+    "insert",
+    "pop",
+    "popleft",
+    "popitem",
+    "remove",
+    "clear",
+    "update",
+    "add",
+    "discard",
+    "setdefault",
+    "sort",
+    "reverse",
+    "rotate",
+];
+
+/// Python constructors of mutable containers, with or without `collections.`
+const PYTHON_CONTAINERS: &[&str] = &[
+    "list",
+    "dict",
+    "set",
+    "bytearray",
+    "defaultdict",
+    "deque",
+    "Counter",
+    "OrderedDict",
+];
+
+/// JavaScript calls that mutate their receiver
+const JS_MUTATORS: &[&str] = &[
+    "push",
+    "pop",
+    "shift",
+    "unshift",
+    "splice",
+    "sort",
+    "reverse",
+    "fill",
+    "copyWithin",
+    "set",
+    "delete",
+    "clear",
+    "add",
+];
+
+/// JavaScript constructors of mutable containers
+const JS_CONTAINERS: &[&str] = &["Map", "Set", "WeakMap", "WeakSet", "Array", "Object"];
+
+/// Exported Next.js route handlers
+const HTTP_METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// Words in a `with` item that make it a lock
+const LOCK_WORDS: &[&str] = &["lock", "mutex", "semaphore"];
+
+/// Methods whose call in a function takes a lock for it
+const LOCK_CALLS: &[&str] = &["acquire", "lock", "runExclusive", "withLock"];
+
+/// Analyzer that reports shared mutable state reached concurrently
+pub struct ConcurrencyAnalyzer;
+
+impl Default for ConcurrencyAnalyzer {
+    fn default() -> Self {
+        Self
+    }
+}
+
+impl ConcurrencyAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// A write to shared state outside any lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mutation {
+    /// The state written: `CACHE`, or `Registry.items` for a class attribute
+    pub var: String,
+    /// Line the state is declared on
+    pub declared: usize,
+    pub line: usize,
+}
+
+/// A mutation of shared state in a function reached from an entry point
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Race {
+    pub family: Family,
+    pub file: PathBuf,
+    /// The function writing: `record`, `Registry.register`
+    pub function: String,
+    pub mutation: Mutation,
+    /// The entry point reaching it: `threading.Thread(target=worker)`,
+    /// `POST /items`
+    pub entry: String,
+    /// Where the entry point is: the spawning call, or the route
+    pub entry_file: PathBuf,
+    pub entry_line: usize,
+}
+
+/// Rust code opting out of the compiler's thread-safety checks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unchecked {
+    /// `static mut NAME`
+    StaticMut { name: String, line: usize },
+    /// `unsafe impl Send for Type` (or `Sync`)
+    SendSync {
+        trait_name: String,
+        type_name: String,
+        line: usize,
+    },
+}
+
+/// A function of a scanned file
+#[derive(Debug)]
+struct Unit {
+    name: String,
+    line: usize,
+    /// Unguarded writes, the first per variable
+    mutations: Vec<Mutation>,
+    /// Functions called by plain name, followed when the graph has no node
+    /// for the unit (inline route callbacks)
+    calls: Vec<String>,
+}
+
+/// A call or route that runs a function concurrently
+#[derive(Debug)]
+struct Entry {
+    label: String,
+    file: PathBuf,
+    line: usize,
+    /// The function run, by name; without one (or when no function of that
+    /// name exists) the function starting on `line`
+    target: Option<String>,
+}
+
+/// What one file declares
+#[derive(Debug, Default)]
+struct Scanned {
+    units: Vec<Unit>,
+    entries: Vec<Entry>,
+}
+
+/// Every reachable mutation of shared state in `graph`, by file and line
+pub fn find_races(graph: &CodeGraph, config: &RevetConfig) -> Vec<Race> {
+    let settings = &config.analyzers.concurrency;
+    let mut scope = Scope::new(graph, config);
+    let root = graph.root_path();
+    let patterns: Vec<&str> = ENTRY_POINTS
+        .iter()
+        .copied()
+        .chain(settings.entry_points.iter().map(String::as_str))
+        .collect();
+
+    let mut files: Vec<&Path> = Vec::new();
+    let mut by_line: HashMap<(&Path, usize), NodeId> = HashMap::new();
+    let mut by_name: HashMap<&str, Vec<NodeId>> = HashMap::new();
+    for (id, node) in graph.nodes() {
+        let file = node.file_path().as_path();
+        match node.kind() {
+            NodeKind::File => files.push(file),
+            NodeKind::Function if scope.includes(file) => {
+                by_line.entry((file, node.line())).or_insert(id);
+                let name = node.name().rsplit('.').next().unwrap_or_default();
+                by_name.entry(name).or_default().push(id);
+            }
+            _ => {}
+        }
+    }
+    files.sort();
+    files.dedup();
+
+    let mut scans: HashMap<&Path, (Family, Scanned)> = HashMap::new();
+    let mut backend = Backend::default();
+    for file in files {
+        let Some(grammar) = language_for(file)
+            .filter(|g| matches!(g.family, Family::Python | Family::Js))
+            .filter(|_| scope.includes(file))
+        else {
+            continue;
+        };
+        let Ok(source) = std::fs::read_to_string(file) else {
+            continue;
+        };
+        let Some(tree) = grammar.parse(&source) else {
+            continue;
+        };
+        let rel = file.strip_prefix(root).unwrap_or(file);
+        if settings.handlers {
+            backend.scan(grammar.family, file, rel, tree.root_node(), &source);
+        }
+        let scanned = match grammar.family {
+            Family::Python => python(file, tree.root_node(), &source, &patterns),
+            _ => js(file, rel, tree.root_node(), &source, settings.handlers),
+        };
+        scans.insert(file, (grammar.family, scanned));
+    }
+
+    let mut entries: Vec<&Entry> = scans.values().flat_map(|(_, s)| &s.entries).collect();
+    let routes: Vec<Entry> = backend
+        .resolve()
+        .0
+        .into_iter()
+        .map(|e| Entry {
+            label: format!("{} {}", e.method, e.path),
+            // Inline callbacks are named after the route call
+            target: Some(e.handler.rsplit('.').next().unwrap_or_default().to_string()),
+            file: e.file,
+            line: e.line,
+        })
+        .collect();
+    entries.extend(&routes);
+    entries.sort_by(|a, b| (&a.file, a.line, &a.label).cmp(&(&b.file, b.line, &b.label)));
+    entries.dedup_by(|a, b| a.file == b.file && a.line == b.line);
+
+    let unit_at = |file: &Path, line: usize| -> Option<(Family, &Unit)> {
+        let (family, scanned) = scans.get(file)?;
+        let unit = scanned.units.iter().find(|u| u.line == line)?;
+        Some((*family, unit))
+    };
+    let same_file = |file: &Path, name: &str| -> Vec<NodeId> {
+        by_name
+            .get(name)
+            .map(|ids| {
+                ids.iter()
+                    .copied()
+                    .filter(|id| graph.node(*id).is_some_and(|n| n.file_path() == file))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    // Breadth-first from every entry point at once; each function keeps the
+    // first entry reaching it
+    let mut reached: BTreeMap<NodeId, usize> = BTreeMap::new();
+    let mut inline: Vec<(&Path, usize, usize)> = Vec::new();
+    let mut queue = VecDeque::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let mut starts = match &entry.target {
+            Some(name) => {
+                let local = same_file(&entry.file, name);
+                if local.is_empty() {
+                    by_name.get(name.as_str()).cloned().unwrap_or_default()
+                } else {
+                    local
+                }
+            }
+            None => Vec::new(),
+        };
+        if starts.is_empty() {
+            match by_line.get(&(entry.file.as_path(), entry.line)) {
+                Some(id) => starts.push(*id),
+                None => {
+                    if let Some((_, unit)) = unit_at(&entry.file, entry.line) {
+                        inline.push((&entry.file, unit.line, index));
+                        for call in &unit.calls {
+                            starts.extend(same_file(&entry.file, call));
+                        }
+                    }
+                }
+            }
+        }
+        for id in starts {
+            if let std::collections::btree_map::Entry::Vacant(slot) = reached.entry(id) {
+                slot.insert(index);
+                queue.push_back((id, 0));
+            }
+        }
+    }
+    while let Some((id, depth)) = queue.pop_front() {
+        if depth >= settings.max_depth {
+            continue;
+        }
+        let index = reached[&id];
+        for (to, edge) in graph.edges_from(id) {
+            if edge.kind() != &EdgeKind::Calls
+                || matches!(edge.metadata(), Some(EdgeMetadata::ExternalCall { .. }))
+                || reached.contains_key(&to)
+            {
+                continue;
+            }
+            let Some(target) = graph.node(to) else {
+                continue;
+            };
+            if target.kind() == &NodeKind::Function && scope.includes(target.file_path()) {
+                reached.insert(to, index);
+                queue.push_back((to, depth + 1));
+            }
+        }
+    }
+
+    let reached_units = reached.iter().filter_map(|(id, index)| {
+        let node = graph.node(*id)?;
+        Some((node.file_path().as_path(), node.line(), *index))
+    });
+    let mut races = Vec::new();
+    let mut seen = HashSet::new();
+    for (file, at, index) in reached_units.chain(inline) {
+        let Some((family, unit)) = unit_at(file, at) else {
+            continue;
+        };
+        let entry = entries[index];
+        for mutation in &unit.mutations {
+            if !seen.insert((file, unit.line, mutation.var.clone())) {
+                continue;
+            }
+            races.push(Race {
+                family,
+                file: file.to_path_buf(),
+                function: unit.name.clone(),
+                mutation: mutation.clone(),
+                entry: entry.label.clone(),
+                entry_file: entry.file.clone(),
+                entry_line: entry.line,
+            });
+        }
+    }
+    races.sort_by(|a, b| (&a.file, a.mutation.line).cmp(&(&b.file, b.mutation.line)));
+    races
+}
+
+/// `static mut` items and `unsafe impl Send`/`Sync` of a Rust file
+pub fn unchecked(root: Node, source: &str) -> Vec<Unchecked> {
+    let mut found = Vec::new();
+    for node in descendants(root) {
+        match node.kind() {
+            "static_item" if has_child(node, "mutable_specifier") => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    found.push(Unchecked::StaticMut {
+                        name: text(name, source).to_string(),
+                        line: line(node),
+                    });
+                }
+            }
+            "impl_item" if has_child(node, "unsafe") => {
+                let (Some(trait_node), Some(type_node)) = (
+                    node.child_by_field_name("trait"),
+                    node.child_by_field_name("type"),
+                ) else {
+                    continue;
+                };
+                let trait_name = text(trait_node, source)
+                    .rsplit("::")
+                    .next()
+                    .unwrap_or_default();
+                if matches!(trait_name, "Send" | "Sync") {
+                    found.push(Unchecked::SendSync {
+                        trait_name: trait_name.to_string(),
+                        type_name: text(type_node, source).to_string(),
+                        line: line(node),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    found
+}
+
+fn has_child(node: Node, kind: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node.children(&mut cursor).any(|c| c.kind() == kind);
+    found
+}
+
+/// Nodes of `node`'s subtree, without the subtrees of nested `skip` kinds
+fn own_nodes<'t>(node: Node<'t>, skip: &[&str]) -> Vec<Node<'t>> {
+    let mut out = Vec::new();
+    let mut stack = vec![node];
+    while let Some(current) = stack.pop() {
+        out.push(current);
+        let mut cursor = current.walk();
+        let children: Vec<Node> = current
+            .children(&mut cursor)
+            .filter(|c| !skip.contains(&c.kind()))
+            .collect();
+        stack.extend(children.into_iter().rev());
+    }
+    out
+}
+
+/// Whether a call named `callee` (`threading.Thread`, `self.pool.submit`)
+/// matches one of `patterns`: the name itself or a dotted suffix of it
+fn is_entry_call(callee: &str, patterns: &[&str]) -> bool {
+    patterns.iter().any(|p| {
+        let p = p.trim_matches('.');
+        callee == p || callee.ends_with(&format!(".{}", p))
+    })
+}
+
+/// The last segment of a dotted name
+fn last_segment(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Module- and class-level state of a Python file
+struct PythonState {
+    /// Module variables: line, and whether they hold a mutable container
+    module: HashMap<String, (usize, bool)>,
+    /// `(class, attribute)` of class-level mutable containers
+    classes: HashMap<(String, String), usize>,
+}
+
+fn python(file: &Path, root: Node, source: &str, patterns: &[&str]) -> Scanned {
+    let mut state = PythonState {
+        module: HashMap::new(),
+        classes: HashMap::new(),
+    };
+    let mut cursor = root.walk();
+    for child in root.named_children(&mut cursor) {
+        let class = match child.kind() {
+            "expression_statement" => {
+                if let Some((name, value)) = python_assignment(child, source) {
+                    let container = value.is_some_and(|v| python_container(v, source));
+                    state.module.entry(name).or_insert((line(child), container));
+                }
+                continue;
+            }
+            "decorated_definition" => child.child_by_field_name("definition"),
+            "class_definition" => Some(child),
+            _ => None,
+        };
+        let Some(class) = class.filter(|c| c.kind() == "class_definition") else {
+            continue;
+        };
+        let (Some(name), Some(body)) = (
+            class.child_by_field_name("name"),
+            class.child_by_field_name("body"),
+        ) else {
+            continue;
+        };
+        let mut cursor = body.walk();
+        for statement in body.named_children(&mut cursor) {
+            if let Some((attribute, Some(value))) = python_assignment(statement, source) {
+                if python_container(value, source) {
+                    state
+                        .classes
+                        .entry((text(name, source).to_string(), attribute))
+                        .or_insert(line(statement));
+                }
+            }
+        }
+    }
+
+    let mut scanned = Scanned::default();
+    for node in descendants(root) {
+        match node.kind() {
+            "function_definition" => {
+                if let Some(unit) = python_unit(node, source, &state) {
+                    scanned.units.push(unit);
+                }
+            }
+            "call" => {
+                if let Some(entry) = python_entry(file, node, source, patterns) {
+                    scanned.entries.push(entry);
+                }
+            }
+            _ => {}
+        }
+    }
+    scanned
+}
+
+/// `name = value` (or `name: T = value`) as a statement
+fn python_assignment<'t>(statement: Node<'t>, source: &str) -> Option<(String, Option<Node<'t>>)> {
+    if statement.kind() != "expression_statement" {
+        return None;
+    }
+    let assignment = statement
+        .named_child(0)
+        .filter(|a| a.kind() == "assignment")?;
+    let left = assignment
+        .child_by_field_name("left")
+        .filter(|l| l.kind() == "identifier")?;
+    Some((
+        text(left, source).to_string(),
+        assignment.child_by_field_name("right"),
+    ))
+}
+
+/// Whether `value` builds a mutable container
+fn python_container(value: Node, source: &str) -> bool {
+    match value.kind() {
+        "list"
+        | "dictionary"
+        | "set"
+        | "list_comprehension"
+        | "dictionary_comprehension"
+        | "set_comprehension" => true,
+        "call" => value.child_by_field_name("function").is_some_and(|f| {
+            let callee = text(f, source);
+            PYTHON_CONTAINERS.contains(&callee.trim_start_matches("collections."))
+        }),
+        _ => false,
+    }
+}
+
+/// The function `def` at `node`, with its unguarded writes to shared state
+fn python_unit(node: Node, source: &str, state: &PythonState) -> Option<Unit> {
+    let name = text(node.child_by_field_name("name")?, source);
+    let body = node.child_by_field_name("body")?;
+    // A method's class: `def` → block → `class`
+    let class = node
+        .parent()
+        .and_then(|b| b.parent())
+        .filter(|c| c.kind() == "class_definition")
+        .and_then(|c| c.child_by_field_name("name"))
+        .map(|n| text(n, source));
+    let own = own_nodes(body, &["function_definition", "class_definition"]);
+
+    let mut globals = HashSet::new();
+    let mut locals = HashSet::new();
+    if let Some(parameters) = node.child_by_field_name("parameters") {
+        let mut cursor = parameters.walk();
+        for parameter in parameters.named_children(&mut cursor) {
+            let name = match parameter.kind() {
+                "identifier" => Some(parameter),
+                "default_parameter" | "typed_default_parameter" => {
+                    parameter.child_by_field_name("name")
+                }
+                _ => parameter
+                    .named_child(0)
+                    .filter(|n| n.kind() == "identifier"),
+            };
+            if let Some(name) = name {
+                locals.insert(text(name, source));
+            }
+        }
+    }
+    for n in &own {
+        match n.kind() {
+            "global_statement" => {
+                let mut cursor = n.walk();
+                globals.extend(n.named_children(&mut cursor).map(|g| text(g, source)));
+            }
+            "assignment" | "augmented_assignment" | "for_statement" => {
+                if let Some(left) = n
+                    .child_by_field_name("left")
+                    .filter(|l| l.kind() == "identifier")
+                {
+                    locals.insert(text(left, source));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // A shared variable by the expression naming it
+    let shared = |target: Node, rebinding: bool| -> Option<(String, usize)> {
+        match target.kind() {
+            "identifier" => {
+                let var = text(target, source);
+                let &(declared, container) = state.module.get(var)?;
+                let visible = globals.contains(var) || (!rebinding && !locals.contains(var));
+                (visible && (container || globals.contains(var)))
+                    .then(|| (var.to_string(), declared))
+            }
+            "attribute" if !rebinding => {
+                let object = text(target.child_by_field_name("object")?, source);
+                let attribute = text(target.child_by_field_name("attribute")?, source);
+                let owner = match object {
+                    "self" | "cls" => class?,
+                    other => other,
+                };
+                let declared = *state
+                    .classes
+                    .get(&(owner.to_string(), attribute.to_string()))?;
+                Some((format!("{}.{}", owner, attribute), declared))
+            }
+            _ => None,
+        }
+    };
+
+    let calls: Vec<Node> = own.iter().copied().filter(|n| n.kind() == "call").collect();
+    let locked = calls.iter().any(|call| {
+        call.child_by_field_name("function")
+            .filter(|f| f.kind() == "attribute")
+            .and_then(|f| f.child_by_field_name("attribute"))
+            .is_some_and(|a| text(a, source) == "acquire")
+    });
+    let mut mutations: Vec<Mutation> = Vec::new();
+    for n in &own {
+        let targets: Vec<(Node, bool)> = match n.kind() {
+            "call" => n
+                .child_by_field_name("function")
+                .filter(|f| f.kind() == "attribute")
+                .filter(|f| {
+                    f.child_by_field_name("attribute")
+                        .is_some_and(|a| PYTHON_MUTATORS.contains(&text(a, source)))
+                })
+                .and_then(|f| f.child_by_field_name("object"))
+                .map(|o| (o, false))
+                .into_iter()
+                .collect(),
+            "assignment" | "augmented_assignment" => match n.child_by_field_name("left") {
+                Some(left) if left.kind() == "subscript" => left
+                    .child_by_field_name("value")
+                    .map(|v| (v, false))
+                    .into_iter()
+                    .collect(),
+                Some(left) if left.kind() == "identifier" => vec![(left, true)],
+                _ => Vec::new(),
+            },
+            "delete_statement" => descendants(*n)
+                .into_iter()
+                .filter(|d| d.kind() == "subscript")
+                .filter_map(|d| d.child_by_field_name("value"))
+                .map(|v| (v, false))
+                .collect(),
+            _ => Vec::new(),
+        };
+        for (target, rebinding) in targets {
+            let Some((var, declared)) = shared(target, rebinding) else {
+                continue;
+            };
+            if locked || in_lock(*n, node, source) || mutations.iter().any(|m| m.var == var) {
+                continue;
+            }
+            mutations.push(Mutation {
+                var,
+                declared,
+                line: line(*n),
+            });
+        }
+    }
+
+    Some(Unit {
+        name: class.map_or(name.to_string(), |c| format!("{}.{}", c, name)),
+        line: line(node),
+        mutations,
+        calls: plain_calls(&calls, source),
+    })
+}
+
+/// Whether `node` sits in a `with` on a lock, within `function`
+fn in_lock(node: Node, function: Node, source: &str) -> bool {
+    let mut current = node.parent();
+    while let Some(n) = current.filter(|n| n.id() != function.id()) {
+        if n.kind() == "with_statement" {
+            let mut cursor = n.walk();
+            let locks = n
+                .children(&mut cursor)
+                .filter(|c| c.kind() == "with_clause")
+                .any(|c| {
+                    let items = text(c, source).to_lowercase();
+                    LOCK_WORDS.iter().any(|w| items.contains(w))
+                });
+            if locks {
+                return true;
+            }
+        }
+        current = n.parent();
+    }
+    false
+}
+
+/// Names of the functions `calls` call by plain name
+fn plain_calls(calls: &[Node], source: &str) -> Vec<String> {
+    let mut names: Vec<String> = calls
+        .iter()
+        .filter_map(|c| c.child_by_field_name("function"))
+        .filter(|f| f.kind() == "identifier")
+        .map(|f| text(f, source).to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// `Thread(target=worker)`, `executor.submit(worker, ...)`: the function a
+/// spawning call runs
+fn python_entry(file: &Path, call: Node, source: &str, patterns: &[&str]) -> Option<Entry> {
+    let callee = text(call.child_by_field_name("function")?, source);
+    if !is_entry_call(callee, patterns) {
+        return None;
+    }
+    let arguments = call.child_by_field_name("arguments")?;
+    let mut cursor = arguments.walk();
+    let arguments: Vec<Node> = arguments.named_children(&mut cursor).collect();
+    let keyword = arguments.iter().find(|a| {
+        a.kind() == "keyword_argument"
+            && a.child_by_field_name("name")
+                .is_some_and(|n| matches!(text(n, source), "target" | "function" | "func" | "fn"))
+    });
+    let (argument, function) = match keyword {
+        Some(keyword) => (*keyword, keyword.child_by_field_name("value")?),
+        None => {
+            let positional = arguments
+                .iter()
+                .find(|a| matches!(a.kind(), "identifier" | "attribute" | "call"))?;
+            (*positional, *positional)
+        }
+    };
+    // `functools.partial(worker, ...)` runs `worker`
+    let function = match function.kind() {
+        "call" => {
+            let inner = function.child_by_field_name("function")?;
+            if last_segment(text(inner, source)) != "partial" {
+                return None;
+            }
+            function.child_by_field_name("arguments")?.named_child(0)?
+        }
+        _ => function,
+    };
+    if !matches!(function.kind(), "identifier" | "attribute") {
+        return None;
+    }
+    Some(Entry {
+        label: format!("{}({})", callee, text(argument, source)),
+        file: file.to_path_buf(),
+        line: line(call),
+        target: Some(last_segment(text(function, source)).to_string()),
+    })
+}
+
+/// Function kinds that are units of their own, wherever they are
+const JS_DECLARATIONS: &[&str] = &[
+    "function_declaration",
+    "generator_function_declaration",
+    "method_definition",
+    "class_declaration",
+    "class",
+];
+
+/// Function expressions, units when not nested in another function
+const JS_EXPRESSIONS: &[&str] = &["arrow_function", "function_expression", "function"];
+
+/// Module-level bindings of a JavaScript file: line, whether they hold a
+/// mutable container, and whether they can be rebound (`let`, `var`)
+type JsState = HashMap<String, (usize, bool, bool)>;
+
+fn js(file: &Path, rel: &Path, root: Node, source: &str, handlers: bool) -> Scanned {
+    let mut state: JsState = HashMap::new();
+    let mut scanned = Scanned::default();
+    let pages_api = rel
+        .to_string_lossy()
+        .replace('\\', "/")
+        .contains("pages/api/");
+    let mut cursor = root.walk();
+    for child in root.named_children(&mut cursor) {
+        let exported = child.kind() == "export_statement";
+        let declaration = if exported {
+            match child.child_by_field_name("declaration") {
+                Some(d) => d,
+                None => {
+                    // `export default async (req, res) => {...}`
+                    if handlers && pages_api && has_child(child, "default") {
+                        if let Some(value) = child.child_by_field_name("value") {
+                            scanned
+                                .entries
+                                .push(js_entry(file, "export default", value, None));
+                        }
+                    }
+                    continue;
+                }
+            }
+        } else {
+            child
+        };
+        match declaration.kind() {
+            "lexical_declaration" | "variable_declaration" => {
+                let rebindable = declaration
+                    .child(0)
+                    .is_some_and(|k| matches!(text(k, source), "let" | "var"));
+                let mut cursor = declaration.walk();
+                for declarator in declaration.named_children(&mut cursor) {
+                    let Some(name) = declarator
+                        .child_by_field_name("name")
+                        .filter(|n| n.kind() == "identifier")
+                    else {
+                        continue;
+                    };
+                    let name = text(name, source);
+                    let value = declarator.child_by_field_name("value");
+                    let container = value.is_some_and(|v| js_container(v, source));
+                    if exported && handlers && HTTP_METHODS.contains(&name) {
+                        if let Some(value) = value {
+                            let label = format!("export {}", name);
+                            scanned
+                                .entries
+                                .push(js_entry(file, &label, value, Some(name)));
+                        }
+                    }
+                    if container || rebindable {
+                        state.entry(name.to_string()).or_insert((
+                            line(declaration),
+                            container,
+                            rebindable,
+                        ));
+                    }
+                }
+            }
+            "function_declaration" if exported && handlers => {
+                let name = declaration
+                    .child_by_field_name("name")
+                    .map(|n| text(n, source));
+                if has_child(child, "default") && pages_api {
+                    scanned
+                        .entries
+                        .push(js_entry(file, "export default", declaration, name));
+                } else if let Some(name) = name.filter(|n| HTTP_METHODS.contains(n)) {
+                    let label = format!("export function {}", name);
+                    scanned
+                        .entries
+                        .push(js_entry(file, &label, declaration, Some(name)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for node in descendants(root) {
+        let unit = if JS_DECLARATIONS[..3].contains(&node.kind()) {
+            true
+        } else if JS_EXPRESSIONS.contains(&node.kind()) {
+            // Nested function expressions belong to their function
+            let mut parent = node.parent();
+            let mut nested = false;
+            while let Some(p) = parent {
+                if JS_DECLARATIONS[..3].contains(&p.kind()) || JS_EXPRESSIONS.contains(&p.kind()) {
+                    nested = true;
+                    break;
+                }
+                parent = p.parent();
+            }
+            !nested
+        } else {
+            false
+        };
+        if unit {
+            scanned.units.push(js_unit(node, source, &state));
+        }
+    }
+    scanned
+}
+
+fn js_entry(file: &Path, label: &str, function: Node, name: Option<&str>) -> Entry {
+    Entry {
+        label: label.to_string(),
+        file: file.to_path_buf(),
+        line: js_unit_line(function),
+        target: name.map(str::to_string),
+    }
+}
+
+/// Whether `value` builds a mutable container
+fn js_container(value: Node, source: &str) -> bool {
+    match value.kind() {
+        "object" | "array" => true,
+        "new_expression" => value
+            .child_by_field_name("constructor")
+            .is_some_and(|c| JS_CONTAINERS.contains(&text(c, source))),
+        _ => false,
+    }
+}
+
+/// The line a function is declared on, as the graph has it: a function
+/// assigned to a variable starts with its declaration
+fn js_unit_line(node: Node) -> usize {
+    node.parent()
+        .filter(|p| p.kind() == "variable_declarator")
+        .and_then(|d| d.parent())
+        .map_or(line(node), line)
+}
+
+/// A function and its unguarded writes to module-level state
+fn js_unit(node: Node, source: &str, state: &JsState) -> Unit {
+    let named = |n: Node| n.child_by_field_name("name").map(|n| text(n, source));
+    let name = match node.kind() {
+        "method_definition" => {
+            let class = node
+                .parent()
+                .and_then(|b| b.parent())
+                .and_then(named)
+                .unwrap_or_default();
+            let method = named(node).unwrap_or_default();
+            if class.is_empty() {
+                method.to_string()
+            } else {
+                format!("{}.{}", class, method)
+            }
+        }
+        "function_declaration" | "generator_function_declaration" => {
+            named(node).unwrap_or_default().to_string()
+        }
+        _ => {
+            let parent = node.parent();
+            let call = parent
+                .filter(|p| p.kind() == "arguments")
+                .and_then(|a| a.parent())
+                .and_then(|c| c.child_by_field_name("function"));
+            match (parent.and_then(named), call) {
+                (Some(name), _) => name.to_string(),
+                (None, Some(call)) => format!("{}(...)", text(call, source)),
+                (None, None) => named(node).unwrap_or("<anonymous>").to_string(),
+            }
+        }
+    };
+    let own = own_nodes(node, JS_DECLARATIONS);
+
+    let mut locals = HashSet::new();
+    for n in &own {
+        match n.kind() {
+            "variable_declarator" => {
+                if let Some(name) = n.child_by_field_name("name") {
+                    locals.insert(text(name, source));
+                }
+            }
+            "formal_parameters" => {
+                locals.extend(
+                    descendants(*n)
+                        .into_iter()
+                        .filter(|d| d.kind() == "identifier")
+                        .map(|d| text(d, source)),
+                );
+            }
+            "arrow_function" => {
+                if let Some(parameter) = n.child_by_field_name("parameter") {
+                    locals.insert(text(parameter, source));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let calls: Vec<Node> = own
+        .iter()
+        .copied()
+        .filter(|n| n.kind() == "call_expression")
+        .collect();
+    let method = |call: &Node| {
+        call.child_by_field_name("function")
+            .filter(|f| f.kind() == "member_expression")
+            .and_then(|f| f.child_by_field_name("property"))
+            .map(|p| text(p, source))
+    };
+    let locked = calls
+        .iter()
+        .any(|c| method(c).is_some_and(|m| LOCK_CALLS.contains(&m)));
+    let mut mutations: Vec<Mutation> = Vec::new();
+    for n in &own {
+        // A write through a member or index writes the object; only a bare
+        // assignment or update rebinds the name itself
+        let (target, rebinds) = match n.kind() {
+            "assignment_expression" | "augmented_assignment_expression" => {
+                (n.child_by_field_name("left"), true)
+            }
+            "update_expression" => (n.child_by_field_name("argument"), true),
+            "unary_expression"
+                if n.child_by_field_name("operator")
+                    .is_some_and(|o| text(o, source) == "delete") =>
+            {
+                (n.child_by_field_name("argument"), false)
+            }
+            "call_expression" if method(n).is_some_and(|m| JS_MUTATORS.contains(&m)) => (
+                n.child_by_field_name("function")
+                    .and_then(|f| f.child_by_field_name("object")),
+                false,
+            ),
+            _ => (None, false),
+        };
+        let Some(target) = target else {
+            continue;
+        };
+        let direct = rebinds && target.kind() == "identifier";
+        let mut root = target;
+        while let Some(object) = match root.kind() {
+            "member_expression" | "subscript_expression" => root.child_by_field_name("object"),
+            "parenthesized_expression" | "non_null_expression" => root.named_child(0),
+            _ => None,
+        } {
+            root = object;
+        }
+        if root.kind() != "identifier" {
+            continue;
+        }
+        let var = text(root, source);
+        let Some(&(declared, container, rebindable)) = state.get(var) else {
+            continue;
+        };
+        // A bare name must be rebindable, a write through it a container
+        if locals.contains(var) || (!rebindable && (direct || !container)) {
+            continue;
+        }
+        if locked || mutations.iter().any(|m| m.var == var) {
+            continue;
+        }
+        mutations.push(Mutation {
+            var: var.to_string(),
+            declared,
+            line: line(*n),
+        });
+    }
+
+    Unit {
+        name,
+        line: js_unit_line(node),
+        mutations,
+        calls: plain_calls(&calls, source),
+    }
+}
+
+fn relative(graph: &CodeGraph, path: &Path) -> String {
+    path.strip_prefix(graph.root_path())
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn severity(config: &ConcurrencyConfig, rule: &str) -> Severity {
+    match config.severity.get(rule).map(String::as_str) {
+        Some("error") => Severity::Error,
+        Some("info") => Severity::Info,
+        _ => Severity::Warning,
+    }
+}
+
+/// Findings for the Rust items of `file`
+fn unchecked_findings(
+    file: &Path,
+    source: &str,
+    config: &ConcurrencyConfig,
+    targets: &mut LanguageTargets,
+) -> Vec<Finding> {
+    let Some(tree) = language_for(file)
+        .filter(|g| g.family == Family::Rust)
+        .and_then(|g| g.parse(source))
+    else {
+        return Vec::new();
+    };
+    let once_lock = targets.for_file(Language::Rust, file).at_least("1.70");
+    unchecked(tree.root_node(), source)
+        .into_iter()
+        .map(|item| match item {
+            Unchecked::StaticMut { name, line } => Finding {
+                rule: Some("static_mut".to_string()),
+                ..catalog_finding(
+                    severity(config, "static_mut"),
+                    msg!("concurrency-static-mut", name = &name),
+                    file.to_path_buf(),
+                    line,
+                    Some(if once_lock {
+                        msg!("concurrency-static-mut.suggestion")
+                    } else {
+                        msg!("concurrency-static-mut.no-oncelock")
+                    }),
+                    None,
+                )
+            },
+            Unchecked::SendSync {
+                trait_name,
+                type_name,
+                line,
+            } => Finding {
+                rule: Some("unsafe_send_sync".to_string()),
+                ..catalog_finding(
+                    severity(config, "unsafe_send_sync"),
+                    msg!(
+                        "concurrency-unsafe-send-sync",
+                        trait_name = &trait_name,
+                        type_name = &type_name
+                    ),
+                    file.to_path_buf(),
+                    line,
+                    Some(msg!("concurrency-unsafe-send-sync.suggestion")),
+                    None,
+                )
+            },
+        })
+        .collect()
+}
+
+impl GraphAnalyzer for ConcurrencyAnalyzer {
+    fn name(&self) -> &str {
+        "Concurrency"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "RACE"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.concurrency
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some(MODULE)
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn analyze_graph(&self, graph: &CodeGraph, config: &RevetConfig) -> Vec<Finding> {
+        let settings = &config.analyzers.concurrency;
+        let mut findings = Vec::new();
+
+        for race in find_races(graph, config) {
+            let mutation = &race.mutation;
+            let (rule, message, suggestion) = match race.family {
+                Family::Python => (
+                    "shared_state",
+                    msg!(
+                        "concurrency-shared-state",
+                        var = &mutation.var,
+                        function = &race.function,
+                        entry = &race.entry
+                    ),
+                    msg!("concurrency-shared-state.suggestion"),
+                ),
+                _ => (
+                    "handler_state",
+                    msg!(
+                        "concurrency-handler-state",
+                        var = &mutation.var,
+                        function = &race.function,
+                        entry = &race.entry
+                    ),
+                    msg!("concurrency-handler-state.suggestion"),
+                ),
+            };
+            findings.push(Finding {
+                rule: Some(rule.to_string()),
+                confidence: Confidence::Medium,
+                details: Some(FindingDetails {
+                    location_list: vec![
+                        format!("{}:{}", relative(graph, &race.entry_file), race.entry_line),
+                        format!("{}:{}", relative(graph, &race.file), mutation.declared),
+                    ],
+                    ..Default::default()
+                }),
+                ..catalog_finding(
+                    severity(settings, rule),
+                    message,
+                    race.file.clone(),
+                    mutation.line,
+                    Some(suggestion),
+                    None,
+                )
+            });
+        }
+
+        let mut scope = Scope::new(graph, config);
+        let mut files: Vec<&Path> = graph
+            .nodes()
+            .filter(|(_, n)| n.kind() == &NodeKind::File)
+            .map(|(_, n)| n.file_path().as_path())
+            .filter(|f| f.extension().is_some_and(|e| e == "rs"))
+            .collect();
+        files.sort();
+        files.dedup();
+        let mut targets = LanguageTargets::new(graph.root_path(), &config.targets);
+        for file in files {
+            if !scope.includes(file) {
+                continue;
+            }
+            if let Ok(source) = std::fs::read_to_string(file) {
+                findings.extend(unchecked_findings(file, &source, settings, &mut targets));
+            }
+        }
+
+        findings
+    }
+
+    fn analyze_file(
+        &self,
+        graph: &CodeGraph,
+        file: &Path,
+        content: &str,
+        config: &RevetConfig,
+    ) -> Vec<Finding> {
+        if !Scope::new(graph, config).includes(file) {
+            return Vec::new();
+        }
+        let mut targets = LanguageTargets::new(graph.root_path(), &config.targets);
+        unchecked_findings(file, content, &config.analyzers.concurrency, &mut targets)
+    }
+}
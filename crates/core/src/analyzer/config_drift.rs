@@ -0,0 +1,507 @@
+//! Configuration drift (`CONF`) — how a diff changes revet's own enforcement
+//!
+//! In diff runs, the resolved configuration of the base ref is compared with
+//! the one the run uses. Resolved means after profiles and flags are layered
+//! on, so a `[general] profile = "quick"` that turns modules off counts the
+//! same as editing `[modules]`. Sub-rules:
+//!
+//! - `modules` — an analyzer module switched off or on
+//! - `severity` — a `[severity]` entry lowered (or set to `off`) or raised
+//! - `thresholds` — `complexity_threshold`, `duplication_min_lines` or a
+//!   `[gate]` maximum raised or lowered
+//! - `excludes` — `[ignore]` paths, findings or per-path rules added or
+//!   removed
+//! - `baseline` — baseline entries added or removed; only compared when the
+//!   base ref has a baseline file
+//! - `fail_on` — the exit threshold loosened or tightened
+//!
+//! Weakening changes are warnings, strengthening ones info. Findings point
+//! at the line of the head `.revet.toml` that makes the change, or its
+//! first line when the change comes from elsewhere (a profile default, a
+//! removed key). The base config decides whether the module runs and how
+//! its findings are filtered, so a change cannot switch off its own report.
+//!
+//! Finding prefix: `CONF-`
+
+use crate::analyzer::catalog_finding;
+use crate::baseline::Baseline;
+use crate::config::{ConfigFile, RevetConfig};
+use crate::diff::blob::GitTreeReader;
+use crate::finding::{Finding, Severity};
+use crate::git_objects::GitObjects;
+use crate::i18n::Msg;
+use crate::msg;
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Module name of configuration drift
+pub const MODULE: &str = "config_drift";
+
+/// Sub-rules of configuration drift
+pub const RULES: &[&str] = &[
+    "modules",
+    "severity",
+    "thresholds",
+    "excludes",
+    "baseline",
+    "fail_on",
+];
+
+/// `[modules]` keys that are thresholds, where a higher value reports less
+const MODULE_THRESHOLDS: &[&str] = &["complexity_threshold", "duplication_min_lines"];
+
+/// One side of the comparison
+#[derive(Debug, Clone)]
+pub struct ConfigSide {
+    /// Resolved with the profile and flags of the run before comparing
+    pub config: RevetConfig,
+    /// The `.revet.toml` it was loaded from, if any
+    pub file: Option<ConfigFile>,
+    /// Entries of the baseline; `None` without a baseline file
+    pub baseline: Option<usize>,
+}
+
+impl ConfigSide {
+    /// The working tree: `config`, loaded for a review of `repo_root`, and
+    /// the baseline at `baseline`
+    pub fn working_tree(repo_root: &Path, config: RevetConfig, baseline: &Path) -> Result<Self> {
+        Ok(Self {
+            config,
+            file: RevetConfig::find_path(repo_root).and_then(|path| ConfigFile::read(&path)),
+            baseline: Baseline::load_from(baseline)?.map(|b| b.entries.len()),
+        })
+    }
+
+    /// The tree of `ref_spec`: the nearest `.revet.toml` to `repo_root` as
+    /// [`RevetConfig::find_and_load`] would find it had the ref been checked
+    /// out, before resolution, and the baseline at `baseline` when it is
+    /// inside the repository. Configs above the repository are read from
+    /// disk, as they do not change with the ref. Blobs are read through
+    /// `objects`.
+    pub fn at_ref(
+        repo_root: &Path,
+        ref_spec: &str,
+        baseline: &Path,
+        objects: &GitObjects,
+    ) -> Result<Self> {
+        let reader = GitTreeReader::new(repo_root)?.with_objects(objects.clone());
+        let mut side = Self {
+            config: RevetConfig::default(),
+            file: None,
+            baseline: None,
+        };
+        for dir in repo_root.ancestors() {
+            let path = dir.join(".revet.toml");
+            let text = match path.strip_prefix(repo_root) {
+                Ok(rel) => reader.read_file_at_ref(ref_spec, rel)?,
+                Err(_) => std::fs::read_to_string(&path).ok(),
+            };
+            if let Some(text) = text {
+                side.config = RevetConfig::parse(&text, &path)?;
+                side.file = Some(ConfigFile::new(path, text));
+                break;
+            }
+        }
+        if let Ok(rel) = baseline.strip_prefix(repo_root) {
+            if let Some(text) = reader.read_file_at_ref(ref_spec, rel)? {
+                let parsed: Baseline = serde_json::from_str(&text)?;
+                side.baseline = Some(parsed.entries.len());
+            }
+        }
+        Ok(side)
+    }
+}
+
+/// Whether the module runs: on at the base or the head, so that turning
+/// it off is itself reported
+pub fn enabled(base: &RevetConfig, head: &RevetConfig) -> bool {
+    base.modules.config_drift || head.modules.config_drift
+}
+
+/// One change, before it becomes a finding
+struct Change {
+    rule: &'static str,
+    weakens: bool,
+    message: Msg,
+    /// Where to look for the line, most specific first
+    anchors: Vec<Anchor>,
+}
+
+enum Anchor {
+    Key(Vec<String>),
+    Entry(Vec<String>, String),
+}
+
+fn key(keys: &[&str]) -> Anchor {
+    Anchor::Key(keys.iter().map(|k| k.to_string()).collect())
+}
+
+fn entry(keys: &[&str], entry: &str) -> Anchor {
+    Anchor::Entry(
+        keys.iter().map(|k| k.to_string()).collect(),
+        entry.to_string(),
+    )
+}
+
+/// The profile key, for changes a profile may have made
+fn profile_anchor() -> Anchor {
+    key(&["general", "profile"])
+}
+
+/// Findings for every difference between `base` and `head` that changes
+/// what revet enforces. `baseline` is where the head baseline is, for
+/// findings about it.
+pub fn compare(base: &ConfigSide, head: &ConfigSide, baseline: &Path) -> Vec<Finding> {
+    let mut changes = Vec::new();
+    modules(&base.config, &head.config, &mut changes);
+    severity(&base.config, &head.config, &mut changes);
+    thresholds(&base.config, &head.config, &mut changes);
+    excludes(&base.config, &head.config, &mut changes);
+    fail_on(&base.config, &head.config, &mut changes);
+
+    // The config the change is in: the head's, or the base's when the
+    // change deletes it
+    let file = head.file.as_ref().or(base.file.as_ref());
+    let mut findings: Vec<Finding> = changes
+        .into_iter()
+        .filter_map(|change| {
+            let file = file?;
+            let line = change
+                .anchors
+                .iter()
+                .find_map(|anchor| match anchor {
+                    Anchor::Key(keys) => file.line_of(&strs(keys)),
+                    Anchor::Entry(keys, entry) => file.line_of_entry(&strs(keys), entry),
+                })
+                .unwrap_or(1);
+            Some(finding(change, file.path.clone(), line))
+        })
+        .collect();
+
+    // A deleted baseline has no entries
+    if let Some(from) = base.baseline {
+        let to = head.baseline.unwrap_or(0);
+        if from != to {
+            let weakens = to > from;
+            let message = if weakens {
+                msg!("config-drift-baseline-grown", from = from, to = to)
+            } else {
+                msg!("config-drift-baseline-shrunk", from = from, to = to)
+            };
+            let change = Change {
+                rule: "baseline",
+                weakens,
+                message,
+                anchors: Vec::new(),
+            };
+            findings.push(finding(change, baseline.to_path_buf(), 1));
+        }
+    }
+    findings
+}
+
+fn strs(keys: &[String]) -> Vec<&str> {
+    keys.iter().map(String::as_str).collect()
+}
+
+fn finding(change: Change, file: PathBuf, line: usize) -> Finding {
+    let (severity, suggestion) = match suggestion_key(change.message.key()) {
+        Some(key) if change.weakens => (
+            Severity::Warning,
+            Some(change.message.clone().with_key(key)),
+        ),
+        _ => (Severity::Info, None),
+    };
+    Finding {
+        rule: Some(change.rule.to_string()),
+        ..catalog_finding(severity, change.message, file, line, suggestion, None)
+    }
+}
+
+/// The suggestion of a weakening message, with the same parameters
+fn suggestion_key(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "config-drift-module-disabled" => "config-drift-module-disabled.suggestion",
+        "config-drift-severity-lowered" => "config-drift-severity-lowered.suggestion",
+        "config-drift-threshold-raised" => "config-drift-threshold-raised.suggestion",
+        "config-drift-path-excluded" => "config-drift-path-excluded.suggestion",
+        "config-drift-finding-ignored" => "config-drift-finding-ignored.suggestion",
+        "config-drift-per-path-added" => "config-drift-per-path-added.suggestion",
+        "config-drift-baseline-grown" => "config-drift-baseline-grown.suggestion",
+        "config-drift-fail-on-loosened" => "config-drift-fail-on-loosened.suggestion",
+        _ => return None,
+    })
+}
+
+/// `[modules]` as a table, for reading switches and thresholds by name
+fn modules_table(config: &RevetConfig) -> toml::Table {
+    toml::Table::try_from(&config.modules).unwrap_or_default()
+}
+
+fn modules(base: &RevetConfig, head: &RevetConfig, changes: &mut Vec<Change>) {
+    let (base, head) = (modules_table(base), modules_table(head));
+    for name in crate::config::ModulesConfig::NAMES {
+        let on = |table: &toml::Table| table.get(*name).and_then(toml::Value::as_bool);
+        let (Some(was), Some(is)) = (on(&base), on(&head)) else {
+            continue;
+        };
+        if was == is {
+            continue;
+        }
+        let message = if is {
+            msg!("config-drift-module-enabled", module = name)
+        } else {
+            msg!("config-drift-module-disabled", module = name)
+        };
+        changes.push(Change {
+            rule: "modules",
+            weakens: !is,
+            message,
+            anchors: vec![key(&["modules", name]), profile_anchor()],
+        });
+    }
+}
+
+/// Strictness of a `[severity]` value: `off` drops the findings
+fn severity_rank(value: &str) -> Option<u8> {
+    match value {
+        "off" => Some(0),
+        "info" => Some(1),
+        "warning" => Some(2),
+        "error" => Some(3),
+        _ => None,
+    }
+}
+
+fn severity(base: &RevetConfig, head: &RevetConfig, changes: &mut Vec<Change>) {
+    let keys: BTreeSet<&String> = base.severity.keys().chain(head.severity.keys()).collect();
+    for id in keys {
+        let was = base.severity.get(id).map(String::as_str);
+        let is = head.severity.get(id).map(String::as_str);
+        // An entry added or removed changes the severity by an amount we
+        // can't know, except that `off` drops everything
+        let weakens = match (was.and_then(severity_rank), is.and_then(severity_rank)) {
+            (Some(was), Some(is)) if was != is => is < was,
+            (None, Some(0)) => true,
+            (Some(0), None) => false,
+            _ => continue,
+        };
+        let (from, to) = (was.unwrap_or("default"), is.unwrap_or("default"));
+        let message = if weakens {
+            msg!(
+                "config-drift-severity-lowered",
+                id = id,
+                from = from,
+                to = to
+            )
+        } else {
+            msg!(
+                "config-drift-severity-raised",
+                id = id,
+                from = from,
+                to = to
+            )
+        };
+        changes.push(Change {
+            rule: "severity",
+            weakens,
+            message,
+            anchors: vec![key(&["severity", id]), key(&["severity"])],
+        });
+    }
+}
+
+/// A threshold's name, base and head values (`None` is no limit), and
+/// anchors
+type Threshold = (String, Option<u64>, Option<u64>, Vec<Anchor>);
+
+fn thresholds(base: &RevetConfig, head: &RevetConfig, changes: &mut Vec<Change>) {
+    let (base_modules, head_modules) = (modules_table(base), modules_table(head));
+    let mut compared: Vec<Threshold> = Vec::new();
+    for name in MODULE_THRESHOLDS {
+        let value = |table: &toml::Table| {
+            table
+                .get(*name)
+                .and_then(toml::Value::as_integer)
+                .map(|v| v as u64)
+        };
+        compared.push((
+            format!("modules.{}", name),
+            value(&base_modules),
+            value(&head_modules),
+            vec![key(&["modules", name]), profile_anchor()],
+        ));
+    }
+    // No `[gate]` maximum is no limit
+    let gate = [
+        ("error_max", base.gate.error_max, head.gate.error_max),
+        ("warning_max", base.gate.warning_max, head.gate.warning_max),
+        ("info_max", base.gate.info_max, head.gate.info_max),
+    ];
+    for (name, was, is) in gate {
+        compared.push((
+            format!("gate.{}", name),
+            was.map(|v| v as u64),
+            is.map(|v| v as u64),
+            vec![key(&["gate", name]), key(&["gate"])],
+        ));
+    }
+
+    for (name, was, is, anchors) in compared {
+        if was == is {
+            continue;
+        }
+        let weakens = match (was, is) {
+            (Some(was), Some(is)) => is > was,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        let show = |v: Option<u64>| v.map_or("unlimited".to_string(), |v| v.to_string());
+        let (from, to) = (show(was), show(is));
+        let message = if weakens {
+            msg!(
+                "config-drift-threshold-raised",
+                key = name,
+                from = from,
+                to = to
+            )
+        } else {
+            msg!(
+                "config-drift-threshold-lowered",
+                key = name,
+                from = from,
+                to = to
+            )
+        };
+        changes.push(Change {
+            rule: "thresholds",
+            weakens,
+            message,
+            anchors,
+        });
+    }
+}
+
+fn excludes(base: &RevetConfig, head: &RevetConfig, changes: &mut Vec<Change>) {
+    // `[ignore] paths` and `findings`, with the messages for an entry added
+    // and one removed
+    let lists = [
+        (
+            "paths",
+            &base.ignore.paths,
+            &head.ignore.paths,
+            "config-drift-path-excluded",
+            "config-drift-path-included",
+        ),
+        (
+            "findings",
+            &base.ignore.findings,
+            &head.ignore.findings,
+            "config-drift-finding-ignored",
+            "config-drift-finding-unignored",
+        ),
+    ];
+    for (field, was, is, added, removed) in lists {
+        for value in is.iter().filter(|v| !was.contains(v)) {
+            changes.push(Change {
+                rule: "excludes",
+                weakens: true,
+                message: Msg::new(added).arg("value", value),
+                anchors: vec![
+                    entry(&["ignore", field], value),
+                    key(&["ignore", field]),
+                    profile_anchor(),
+                ],
+            });
+        }
+        for value in was.iter().filter(|v| !is.contains(v)) {
+            changes.push(Change {
+                rule: "excludes",
+                weakens: false,
+                message: Msg::new(removed).arg("value", value),
+                anchors: vec![key(&["ignore", field]), key(&["ignore"])],
+            });
+        }
+    }
+
+    // Per-path rules, compared rule by rule
+    let per_path = |config: &RevetConfig| -> BTreeMap<String, BTreeSet<String>> {
+        config
+            .ignore
+            .per_path
+            .iter()
+            .map(|(glob, rules)| (glob.clone(), rules.iter().cloned().collect()))
+            .collect()
+    };
+    let (was, is) = (per_path(base), per_path(head));
+    let globs: BTreeSet<&String> = was.keys().chain(is.keys()).collect();
+    let none = BTreeSet::new();
+    for glob in globs {
+        let (was, is) = (
+            was.get(glob).unwrap_or(&none),
+            is.get(glob).unwrap_or(&none),
+        );
+        let added: Vec<&str> = is.difference(was).map(String::as_str).collect();
+        let removed: Vec<&str> = was.difference(is).map(String::as_str).collect();
+        let anchors = || {
+            vec![
+                key(&["ignore", "per_path", glob]),
+                key(&["ignore", "per_path"]),
+            ]
+        };
+        if !added.is_empty() {
+            changes.push(Change {
+                rule: "excludes",
+                weakens: true,
+                message: msg!(
+                    "config-drift-per-path-added",
+                    rules = added.join(" "),
+                    glob = glob
+                ),
+                anchors: anchors(),
+            });
+        }
+        if !removed.is_empty() {
+            changes.push(Change {
+                rule: "excludes",
+                weakens: false,
+                message: msg!(
+                    "config-drift-per-path-removed",
+                    rules = removed.join(" "),
+                    glob = glob
+                ),
+                anchors: anchors(),
+            });
+        }
+    }
+}
+
+/// Strictness of `fail_on`: `info` fails on the most
+fn fail_on_rank(value: &str) -> u8 {
+    match value {
+        "info" => 3,
+        "warning" => 2,
+        "error" => 1,
+        _ => 0,
+    }
+}
+
+fn fail_on(base: &RevetConfig, head: &RevetConfig, changes: &mut Vec<Change>) {
+    let (was, is) = (&base.general.fail_on, &head.general.fail_on);
+    if was == is {
+        return;
+    }
+    let weakens = fail_on_rank(is) < fail_on_rank(was);
+    let message = if weakens {
+        msg!("config-drift-fail-on-loosened", from = was, to = is)
+    } else {
+        msg!("config-drift-fail-on-tightened", from = was, to = is)
+    };
+    changes.push(Change {
+        rule: "fail_on",
+        weakens,
+        message,
+        anchors: vec![key(&["general", "fail_on"]), profile_anchor()],
+    });
+}
@@ -0,0 +1,444 @@
+//! API contract analyzer — matches frontend API calls to the backend
+//! endpoints of the same repository
+//!
+//! Endpoints come from the backend's route declarations (see [`endpoints`]):
+//! FastAPI and Flask decorators, with the prefixes of the routers and
+//! blueprints they are mounted under, and Express route registrations.
+//! Client calls come from the frontend (see [`calls`]): `fetch` and `axios`
+//! calls whose URL is a literal or a template literal, and — when a
+//! `[[analyzers.generated]]` entry pairs an OpenAPI spec with its client —
+//! calls of the generated client's methods, located through the spec's
+//! `operationId`s (see [`spec`]).
+//!
+//! Calls and endpoints are matched by method and path template. Parameter
+//! segments are normalised (`/users/{id}`, `/users/:id`, `/users/<int:id>`
+//! and `` `/users/${id}` `` are the same path), and the `base_paths` the
+//! frontend prefixes (`/api`) are stripped from client URLs when matching.
+//!
+//! - `unmatched_call`: a client call no endpoint serves — a typo, or a route
+//!   that was renamed or removed
+//! - `uncalled_endpoint`: an endpoint no client call in the repository
+//!   reaches. Informational: other consumers may call it
+//! - `field_mismatch`: a request body whose fields (an object literal, or the
+//!   TypeScript interface it is declared as) do not match the Pydantic model
+//!   the handler takes — fields the model does not accept, or required ones
+//!   the body lacks. Medium confidence: the body may be filled in elsewhere
+//! - `dynamic_url`: a call whose URL is computed at runtime, which cannot be
+//!   checked
+//!
+//! Nothing is reported for a repository without endpoints, and endpoints
+//! are only reported as uncalled when the repository makes client calls.
+//!
+//! Finding prefix: `CONTRACT-`
+
+pub mod calls;
+pub mod endpoints;
+pub mod spec;
+
+use crate::analyzer::suppressions::edit_distance;
+use crate::analyzer::{catalog_finding, Analyzer};
+use crate::config::{ContractConfig, GeneratedCodeConfig, RevetConfig};
+use crate::finding::{Confidence, Finding, Severity};
+use crate::msg;
+use calls::{Body, Call, Url};
+use endpoints::{Endpoint, Model};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Module name of the API contract analyzer
+pub const MODULE: &str = "contract";
+
+/// Sub-rules of the API contract analyzer
+pub const RULES: &[&str] = &[
+    "unmatched_call",
+    "uncalled_endpoint",
+    "field_mismatch",
+    "dynamic_url",
+];
+
+/// Dynamic URL expressions longer than this are shortened in messages
+const MAX_EXPR: usize = 60;
+
+/// The segments of a path template, each parameter as `{}`:
+/// `/users/:id/orders/` is `["users", "{}", "orders"]`
+pub fn template(path: &str) -> Vec<String> {
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let param = (s.starts_with('{') && s.ends_with('}'))
+                || (s.starts_with('<') && s.ends_with('>'))
+                || s.starts_with(':')
+                || s.contains("${");
+            if param {
+                "{}".to_string()
+            } else {
+                s.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Whether two templates name the same path
+fn same_path(a: &[String], b: &[String]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(x, y)| x == y || x == "{}" || y == "{}")
+}
+
+/// The template as written in messages: `/users/{}`
+fn display(template: &[String]) -> String {
+    format!("/{}", template.join("/"))
+}
+
+/// `file:line`, relative to the repository root
+fn location(file: &Path, line: usize, repo_root: &Path) -> String {
+    let rel = file.strip_prefix(repo_root).unwrap_or(file);
+    format!("{}:{}", rel.display(), line)
+}
+
+/// Analyzer that checks frontend API calls against the repository's
+/// backend endpoints
+pub struct ContractAnalyzer {
+    base_paths: Vec<Vec<String>>,
+    generated: Vec<GeneratedCodeConfig>,
+    unmatched_call: Severity,
+    uncalled_endpoint: Severity,
+    field_mismatch: Severity,
+    dynamic_url: Severity,
+}
+
+impl ContractAnalyzer {
+    /// Create an API contract analyzer with the default settings
+    pub fn new() -> Self {
+        Self::with_config(&ContractConfig::default())
+    }
+
+    /// Create an API contract analyzer honouring `[analyzers.contract]`
+    pub fn with_config(config: &ContractConfig) -> Self {
+        let severity =
+            |rule: &str, default: Severity| match config.severity.get(rule).map(String::as_str) {
+                Some("error") => Severity::Error,
+                Some("warning") => Severity::Warning,
+                Some("info") => Severity::Info,
+                _ => default,
+            };
+        let mut base_paths: Vec<Vec<String>> =
+            config.base_paths.iter().map(|p| template(p)).collect();
+        // Longest first, so `/api/v2` is stripped before `/api`
+        base_paths.sort_by_key(|p| std::cmp::Reverse(p.len()));
+        Self {
+            base_paths,
+            generated: Vec::new(),
+            unmatched_call: severity("unmatched_call", Severity::Error),
+            uncalled_endpoint: severity("uncalled_endpoint", Severity::Info),
+            field_mismatch: severity("field_mismatch", Severity::Warning),
+            dynamic_url: severity("dynamic_url", Severity::Info),
+        }
+    }
+
+    /// Also match calls of the clients generated from these specs
+    pub fn with_generated(mut self, generated: &[GeneratedCodeConfig]) -> Self {
+        self.generated = generated.to_vec();
+        self
+    }
+
+    /// Whether `path` lies in a generated client's output directory
+    fn is_generated(&self, path: &Path, repo_root: &Path) -> bool {
+        let rel = path.strip_prefix(repo_root).unwrap_or(path);
+        self.generated
+            .iter()
+            .any(|g| rel.starts_with(g.output_dir.trim_start_matches("./")))
+    }
+
+    /// The call's path as the backend sees it: as written, then without
+    /// each configured base path it starts with
+    fn candidates(&self, path: &[String]) -> Vec<Vec<String>> {
+        let mut candidates = vec![path.to_vec()];
+        for base in &self.base_paths {
+            if path.len() > base.len() && same_path(&path[..base.len()], base) {
+                candidates.push(path[base.len()..].to_vec());
+            }
+        }
+        candidates
+    }
+
+    fn unmatched(
+        &self,
+        file: &Path,
+        call: &Call,
+        path: &[String],
+        endpoints: &[Endpoint],
+        repo_root: &Path,
+    ) -> Finding {
+        let method = call.method.as_deref().unwrap_or("GET");
+        let written = match &call.url {
+            Url::Path(written) => written.split(['?', '#']).next().unwrap_or(written),
+            Url::Dynamic(expr) => expr,
+        };
+        let route = format!("{} {}", method, written);
+        let candidates = self.candidates(path);
+        // Served with another method
+        let methods: BTreeSet<&str> = endpoints
+            .iter()
+            .filter(|e| candidates.iter().any(|c| same_path(c, &e.template)))
+            .map(|e| e.method.as_str())
+            .collect();
+        let message = if methods.is_empty() {
+            msg!(
+                "contract-unmatched-call",
+                route = &route,
+                callee = call.callee.as_str()
+            )
+        } else {
+            let methods: Vec<&str> = methods.into_iter().collect();
+            msg!(
+                "contract-unmatched-call.method",
+                route = &route,
+                callee = call.callee.as_str(),
+                methods = methods.join(", ")
+            )
+        };
+        let stripped = candidates.last().map(|c| display(c)).unwrap_or_default();
+        let closest = endpoints
+            .iter()
+            .map(|e| (edit_distance(&stripped, &display(&e.template)), e))
+            .filter(|(distance, e)| *distance <= e.path.len().max(4) / 3)
+            .min_by_key(|(distance, e)| (*distance, e.file.clone(), e.line));
+        let suggestion = match closest {
+            Some((_, endpoint)) => msg!(
+                "contract-unmatched-call.closest",
+                endpoint = format!("{} {}", endpoint.method, endpoint.path),
+                location = location(&endpoint.file, endpoint.line, repo_root)
+            ),
+            None => msg!("contract-unmatched-call.suggestion"),
+        };
+        Finding {
+            rule: Some("unmatched_call".to_string()),
+            ..catalog_finding(
+                self.unmatched_call,
+                message,
+                file.to_path_buf(),
+                call.line,
+                Some(suggestion),
+                None,
+            )
+        }
+    }
+
+    fn uncalled(&self, endpoint: &Endpoint) -> Finding {
+        let route = format!("{} {}", endpoint.method, endpoint.path);
+        Finding {
+            rule: Some("uncalled_endpoint".to_string()),
+            confidence: Confidence::Medium,
+            ..catalog_finding(
+                self.uncalled_endpoint,
+                msg!(
+                    "contract-uncalled-endpoint",
+                    route = &route,
+                    handler = endpoint.handler.as_str()
+                ),
+                endpoint.file.clone(),
+                endpoint.line,
+                Some(msg!("contract-uncalled-endpoint.suggestion")),
+                None,
+            )
+        }
+    }
+
+    fn dynamic(&self, file: &Path, call: &Call, expr: &str) -> Finding {
+        let expr: String = expr.split_whitespace().collect::<Vec<_>>().join(" ");
+        let expr = match expr.char_indices().nth(MAX_EXPR) {
+            Some((end, _)) => format!("{}…", &expr[..end]),
+            None => expr,
+        };
+        Finding {
+            rule: Some("dynamic_url".to_string()),
+            confidence: Confidence::Low,
+            ..catalog_finding(
+                self.dynamic_url,
+                msg!(
+                    "contract-dynamic-url",
+                    callee = call.callee.as_str(),
+                    expr = expr
+                ),
+                file.to_path_buf(),
+                call.line,
+                Some(msg!("contract-dynamic-url.suggestion")),
+                None,
+            )
+        }
+    }
+
+    /// A finding when the fields the call sends and the fields the
+    /// handler's model takes disagree
+    fn fields(
+        &self,
+        file: &Path,
+        call: &Call,
+        endpoint: &Endpoint,
+        sent: &[String],
+        models: &HashMap<String, Model>,
+        repo_root: &Path,
+    ) -> Option<Finding> {
+        let model = models.get(endpoint.body.as_deref()?)?;
+        let accepted = endpoints::model_fields(model, models);
+        let extra: Vec<&str> = sent
+            .iter()
+            .filter(|f| !accepted.iter().any(|a| a.name == **f))
+            .map(String::as_str)
+            .collect();
+        let missing: Vec<&str> = accepted
+            .iter()
+            .filter(|a| a.required && !sent.contains(&a.name))
+            .map(|a| a.name.as_str())
+            .collect();
+        let route = format!("{} {}", endpoint.method, endpoint.path);
+        let message = match (extra.is_empty(), missing.is_empty()) {
+            (true, true) => return None,
+            (false, true) => msg!(
+                "contract-field-mismatch.extra",
+                route = &route,
+                model = model.name.as_str(),
+                extra = extra.join(", ")
+            ),
+            (true, false) => msg!(
+                "contract-field-mismatch.missing",
+                route = &route,
+                model = model.name.as_str(),
+                missing = missing.join(", ")
+            ),
+            (false, false) => msg!(
+                "contract-field-mismatch",
+                route = &route,
+                model = model.name.as_str(),
+                extra = extra.join(", "),
+                missing = missing.join(", ")
+            ),
+        };
+        Some(Finding {
+            rule: Some("field_mismatch".to_string()),
+            confidence: Confidence::Medium,
+            ..catalog_finding(
+                self.field_mismatch,
+                message,
+                file.to_path_buf(),
+                call.line,
+                Some(msg!(
+                    "contract-field-mismatch.suggestion",
+                    model = model.name.as_str(),
+                    location = location(&model.file, model.line, repo_root)
+                )),
+                None,
+            )
+        })
+    }
+}
+
+impl Default for ContractAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer for ContractAnalyzer {
+    fn name(&self) -> &str {
+        "API contract"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "CONTRACT"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.contract
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some(MODULE)
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        endpoints::grammar(path).is_some()
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], repo_root: &Path) -> Vec<Finding> {
+        let operations = spec::operations(&self.generated, repo_root);
+        let mut backend = endpoints::Backend::default();
+        let mut client: Vec<(PathBuf, Call)> = Vec::new();
+        let mut interfaces = HashMap::new();
+        for file in files {
+            if self.is_generated(file, repo_root) {
+                continue;
+            }
+            let Some(grammar) = endpoints::grammar(file) else {
+                continue;
+            };
+            let Ok(content) = std::fs::read_to_string(file) else {
+                continue;
+            };
+            let Some(tree) = grammar.parse(&content) else {
+                continue;
+            };
+            let rel = file.strip_prefix(repo_root).unwrap_or(file);
+            backend.scan(grammar.family, file, rel, tree.root_node(), &content);
+            let scanned = calls::scan(grammar.family, tree.root_node(), &content, &operations);
+            interfaces.extend(scanned.interfaces);
+            client.extend(scanned.calls.into_iter().map(|c| (file.clone(), c)));
+        }
+        let (endpoints, models) = backend.resolve();
+        // Without endpoints there is nothing to check calls against
+        if endpoints.is_empty() {
+            return Vec::new();
+        }
+
+        let mut findings = Vec::new();
+        let mut called = vec![false; endpoints.len()];
+        let mut checked = false;
+        for (file, call) in &client {
+            let path = match &call.url {
+                Url::Path(path) => template(path),
+                Url::Dynamic(expr) => {
+                    findings.push(self.dynamic(file, call, expr));
+                    continue;
+                }
+            };
+            checked = true;
+            let candidates = self.candidates(&path);
+            let matched = endpoints
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| call.method.as_ref().is_none_or(|m| *m == e.method))
+                .filter(|(_, e)| candidates.iter().any(|c| same_path(c, &e.template)))
+                .max_by_key(|(_, e)| e.template.iter().filter(|s| *s != "{}").count());
+            let Some((index, endpoint)) = matched else {
+                findings.push(self.unmatched(file, call, &path, &endpoints, repo_root));
+                continue;
+            };
+            called[index] = true;
+            let sent = match &call.body {
+                Some(Body::Fields(fields)) => Some(fields.clone()),
+                Some(Body::Type(name)) => calls::interface_fields(name, &interfaces),
+                None => None,
+            };
+            if let Some(sent) = sent {
+                findings.extend(self.fields(file, call, endpoint, &sent, &models, repo_root));
+            }
+        }
+        if checked {
+            findings.extend(
+                endpoints
+                    .iter()
+                    .zip(&called)
+                    .filter(|(_, called)| !**called)
+                    .map(|(e, _)| self.uncalled(e)),
+            );
+        }
+        findings
+    }
+}
@@ -0,0 +1,597 @@
+//! Frontend API calls and the request bodies they send
+//!
+//! | Call | Method | Body |
+//! |------|--------|------|
+//! | `fetch(url, { method, body: JSON.stringify(x) })` | `method`, or `GET` | `x` |
+//! | `axios.post(url, data)`, `api.put(url, data)` with `api = axios.create({ baseURL })` | the method called | `data` |
+//! | `axios({ url, method, data })`, `axios.request({...})` | `method`, or `GET` | `data` |
+//! | `UsersService.createUser(...)` of a generated client | the spec operation's | — |
+//!
+//! A URL is a string, a template literal, a `+` concatenation or a constant
+//! holding one. An expression leading the URL (`` `${API_URL}/users` ``) is
+//! taken as the origin; one elsewhere is a path parameter. URLs to another
+//! host, and to files (`/locales/en.json`), are not API calls. A URL that is
+//! computed otherwise is [`Url::Dynamic`].
+//!
+//! A body's fields are the keys of an object literal, or the properties of
+//! the interface or type alias it is declared as (`const body: NewUser = ...`,
+//! `{ ... } as NewUser`, a parameter `user: NewUser`), inherited ones
+//! included. Spread objects have no known fields.
+
+use super::spec::{operation_key, Operation};
+use crate::analyzer::websec::{descendants, line, text, unquote};
+use crate::parser::grammar::Family;
+use std::collections::{HashMap, HashSet};
+use tree_sitter::Node;
+
+/// Interfaces extended through more levels than this are not followed
+const MAX_DEPTH: usize = 8;
+
+/// Methods of `axios` and of its instances, and whether they take a body
+const AXIOS_METHODS: &[(&str, bool)] = &[
+    ("get", false),
+    ("delete", false),
+    ("head", false),
+    ("options", false),
+    ("post", true),
+    ("put", true),
+    ("patch", true),
+];
+
+/// Where a call goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Url {
+    /// A path, parameters as `${}` or as written
+    Path(String),
+    /// The expression a URL computed at runtime comes from
+    Dynamic(String),
+}
+
+/// What a call sends
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Body {
+    /// Keys of an object literal
+    Fields(Vec<String>),
+    /// An interface or type alias
+    Type(String),
+}
+
+/// A client call of a backend endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    /// Upper case; `None` when it is computed
+    pub method: Option<String>,
+    pub url: Url,
+    /// The function called: `fetch`, `api.post`, `UsersService.createUser`
+    pub callee: String,
+    pub body: Option<Body>,
+    pub line: usize,
+}
+
+/// A TypeScript interface or object type alias
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interface {
+    pub fields: Vec<String>,
+    pub extends: Vec<String>,
+}
+
+/// Calls and interfaces of one file
+#[derive(Debug, Default)]
+pub struct Scanned {
+    pub calls: Vec<Call>,
+    pub interfaces: HashMap<String, Interface>,
+}
+
+/// The fields of interface `name`, inherited ones first
+pub fn interface_fields(
+    name: &str,
+    interfaces: &HashMap<String, Interface>,
+) -> Option<Vec<String>> {
+    fn collect(
+        name: &str,
+        interfaces: &HashMap<String, Interface>,
+        depth: usize,
+        out: &mut Vec<String>,
+    ) -> bool {
+        let Some(interface) = interfaces.get(name) else {
+            return false;
+        };
+        if depth >= MAX_DEPTH {
+            return false;
+        }
+        for parent in &interface.extends {
+            // An interface extending an unknown type has unknown fields
+            if !collect(parent, interfaces, depth + 1, out) {
+                return false;
+            }
+        }
+        for field in &interface.fields {
+            if !out.contains(field) {
+                out.push(field.clone());
+            }
+        }
+        true
+    }
+    let mut fields = Vec::new();
+    collect(name, interfaces, 0, &mut fields).then_some(fields)
+}
+
+/// Client calls and interfaces in `source`
+pub fn scan(family: Family, root: Node, source: &str, operations: &[Operation]) -> Scanned {
+    if family != Family::Js {
+        return Scanned::default();
+    }
+    let scanner = Scanner::new(root, source, operations);
+    let mut scanned = Scanned::default();
+    for node in descendants(root) {
+        match node.kind() {
+            "interface_declaration" | "type_alias_declaration" => {
+                if let Some((name, interface)) = scanner.interface(node) {
+                    scanned.interfaces.insert(name, interface);
+                }
+            }
+            "call_expression" => scanned.calls.extend(scanner.call(node)),
+            _ => {}
+        }
+    }
+    scanned
+}
+
+struct Scanner<'t, 'a> {
+    source: &'a str,
+    operations: HashMap<String, &'a Operation>,
+    /// `const USERS = "/api/users"`
+    constants: HashMap<String, String>,
+    /// `const api = axios.create({ baseURL: "/api" })`: name → base path
+    instances: HashMap<String, String>,
+    /// Names imported into the file
+    imported: HashSet<String>,
+    /// Variable declarators and parameters, to find what a name holds
+    declarations: Vec<Node<'t>>,
+}
+
+impl<'t, 'a> Scanner<'t, 'a> {
+    fn new(root: Node<'t>, source: &'a str, operations: &'a [Operation]) -> Self {
+        let mut scanner = Self {
+            source,
+            operations: operations
+                .iter()
+                .map(|o| (operation_key(&o.id), o))
+                .collect(),
+            constants: HashMap::new(),
+            instances: HashMap::new(),
+            imported: HashSet::new(),
+            declarations: Vec::new(),
+        };
+        for node in descendants(root) {
+            match node.kind() {
+                "variable_declarator" => {
+                    scanner.declarations.push(node);
+                    scanner.declarator(node);
+                }
+                "required_parameter" | "optional_parameter" => scanner.declarations.push(node),
+                "import_specifier" | "import_clause" | "namespace_import" => {
+                    let mut cursor = node.walk();
+                    for name in node.named_children(&mut cursor) {
+                        if name.kind() == "identifier" {
+                            scanner.imported.insert(text(name, source).to_string());
+                        }
+                    }
+                    if let Some(alias) = node.child_by_field_name("alias") {
+                        scanner.imported.insert(text(alias, source).to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        scanner
+    }
+
+    fn text(&self, node: Node) -> &'a str {
+        text(node, self.source)
+    }
+
+    /// Record string constants and axios instances
+    fn declarator(&mut self, node: Node) {
+        let (Some(name), Some(value)) = (
+            node.child_by_field_name("name")
+                .filter(|n| n.kind() == "identifier"),
+            node.child_by_field_name("value"),
+        ) else {
+            return;
+        };
+        let name = self.text(name).to_string();
+        if let Some(value) = self.literal(value) {
+            self.constants.insert(name, value);
+        } else if value.kind() == "call_expression"
+            && value
+                .child_by_field_name("function")
+                .is_some_and(|f| self.text(f) == "axios.create")
+        {
+            let base = self
+                .arguments(value)
+                .first()
+                .and_then(|config| self.property(*config, "baseURL"))
+                .and_then(|base| match self.url(base) {
+                    Some(Url::Path(path)) => Some(path),
+                    _ => None,
+                })
+                .unwrap_or_default();
+            self.instances.insert(name, base);
+        }
+    }
+
+    /// A string without substitutions
+    fn literal(&self, node: Node) -> Option<String> {
+        match node.kind() {
+            "string" => Some(unquote(self.text(node)).to_string()),
+            "template_string"
+                if !descendants(node)
+                    .iter()
+                    .any(|n| n.kind() == "template_substitution") =>
+            {
+                Some(unquote(self.text(node)).to_string())
+            }
+            _ => None,
+        }
+    }
+
+    fn arguments(&self, call: Node<'t>) -> Vec<Node<'t>> {
+        call.child_by_field_name("arguments")
+            .map(|list| {
+                let mut cursor = list.walk();
+                list.named_children(&mut cursor)
+                    .filter(|a| a.kind() != "comment")
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The value of `key` in an object literal
+    fn property(&self, object: Node<'t>, key: &str) -> Option<Node<'t>> {
+        if object.kind() != "object" {
+            return None;
+        }
+        let mut cursor = object.walk();
+        let found = object
+            .named_children(&mut cursor)
+            .find_map(|p| match p.kind() {
+                "pair"
+                    if p.child_by_field_name("key")
+                        .is_some_and(|k| unquote(self.text(k)) == key) =>
+                {
+                    p.child_by_field_name("value")
+                }
+                "shorthand_property_identifier" if self.text(p) == key => Some(p),
+                _ => None,
+            });
+        found
+    }
+
+    /// The call's method, URL and body when it is an API call
+    fn call(&self, call: Node<'t>) -> Option<Call> {
+        let function = call.child_by_field_name("function")?;
+        let callee = self.text(function);
+        let arguments = self.arguments(call);
+        let make = |method: Option<String>, url: Node<'t>, body: Option<Node<'t>>| {
+            Some(Call {
+                method,
+                url: self.url(url)?,
+                callee: callee.to_string(),
+                body: body.and_then(|b| self.body(b, call.start_byte())),
+                line: line(call),
+            })
+        };
+        if matches!(callee, "fetch" | "window.fetch" | "globalThis.fetch") {
+            let url = *arguments.first()?;
+            return match arguments.get(1) {
+                None => make(Some("GET".to_string()), url, None),
+                Some(options) if options.kind() == "object" => {
+                    let body = self.property(*options, "body").map(|b| self.stringified(b));
+                    make(self.method(*options), url, body)
+                }
+                Some(_) => make(None, url, None),
+            };
+        }
+        let (object, property) = match function.kind() {
+            "member_expression" => (
+                function.child_by_field_name("object").map(|o| self.text(o)),
+                function
+                    .child_by_field_name("property")
+                    .map_or("", |p| self.text(p)),
+            ),
+            _ => (None, callee),
+        };
+        let base = match object {
+            Some("axios") => Some(String::new()),
+            Some(name) => self.instances.get(name).cloned(),
+            None if callee == "axios" => Some(String::new()),
+            None => self.instances.get(callee).cloned(),
+        };
+        if let Some(base) = base {
+            let found = match AXIOS_METHODS.iter().find(|(m, _)| *m == property) {
+                Some((method, takes_body)) if object.is_some() => make(
+                    Some(method.to_uppercase()),
+                    *arguments.first()?,
+                    arguments.get(1).copied().filter(|_| *takes_body),
+                )?,
+                // `axios(config)`, `axios(url, config)`, `axios.request(config)`
+                _ if object.is_none() || property == "request" => match arguments.as_slice() {
+                    [url, config, ..] if url.kind() != "object" => {
+                        make(self.method(*config), *url, self.property(*config, "data"))?
+                    }
+                    [config, ..] => make(
+                        self.method(*config),
+                        self.property(*config, "url")?,
+                        self.property(*config, "data"),
+                    )?,
+                    [] => return None,
+                },
+                _ => return None,
+            };
+            return Some(self.based(found, &base));
+        }
+        // A generated client's operation
+        let operation = self.operations.get(&operation_key(property))?;
+        let known = match object {
+            Some(_) => true,
+            None => self.imported.contains(callee),
+        };
+        known.then(|| Call {
+            method: Some(operation.method.clone()),
+            url: Url::Path(operation.path.clone()),
+            callee: callee.to_string(),
+            body: None,
+            line: line(call),
+        })
+    }
+
+    /// `call` with an instance's base path put in front of its URL
+    fn based(&self, mut call: Call, base: &str) -> Call {
+        if let Url::Path(path) = &call.url {
+            if !base.is_empty() {
+                call.url = Url::Path(format!(
+                    "{}/{}",
+                    base.trim_end_matches('/'),
+                    path.trim_start_matches('/')
+                ));
+            }
+        }
+        call
+    }
+
+    /// `method: "POST"` of an options object; `GET` when left out, `None`
+    /// when computed
+    fn method(&self, options: Node) -> Option<String> {
+        if options.kind() != "object" {
+            return None;
+        }
+        match self.property(options, "method") {
+            None => Some("GET".to_string()),
+            Some(value) => self.literal(value).map(|m| m.to_uppercase()),
+        }
+    }
+
+    /// `x` of `JSON.stringify(x)`
+    fn stringified(&self, body: Node<'t>) -> Node<'t> {
+        if body.kind() == "call_expression"
+            && body
+                .child_by_field_name("function")
+                .is_some_and(|f| self.text(f) == "JSON.stringify")
+        {
+            if let Some(value) = self.arguments(body).first() {
+                return *value;
+            }
+        }
+        body
+    }
+
+    /// The URL an expression evaluates to; `None` when it is not the
+    /// repository's API
+    fn url(&self, node: Node) -> Option<Url> {
+        let mut pieces = Vec::new();
+        if !self.pieces(node, &mut pieces) {
+            return Some(Url::Dynamic(self.text(node).to_string()));
+        }
+        pieces.retain(|p| !matches!(p, Piece::Text(text) if text.is_empty()));
+        // A leading expression is the origin
+        if let [Piece::Expr, Piece::Text(rest), ..] = pieces.as_slice() {
+            if rest.starts_with('/') {
+                pieces.remove(0);
+            }
+        }
+        let mut path = String::new();
+        for piece in &pieces {
+            match piece {
+                Piece::Text(text) => path.push_str(text),
+                Piece::Expr if path.is_empty() => {
+                    return Some(Url::Dynamic(self.text(node).to_string()))
+                }
+                Piece::Expr => path.push_str("${}"),
+            }
+        }
+        let path = match path.split_once("://") {
+            Some((_, rest)) => {
+                let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+                let host = host.split(':').next().unwrap_or(host);
+                if !matches!(host, "localhost" | "127.0.0.1" | "0.0.0.0") {
+                    return None;
+                }
+                format!("/{}", path)
+            }
+            None if path.starts_with("//") => return None,
+            None => path,
+        };
+        let last = path
+            .split(['?', '#'])
+            .next()
+            .unwrap_or("")
+            .rsplit('/')
+            .next()
+            .unwrap_or("");
+        if last.contains('.') && !last.contains("${") {
+            return None;
+        }
+        Some(Url::Path(path))
+    }
+
+    /// Flatten a URL expression into text and expressions; `false` when
+    /// some part is neither
+    fn pieces(&self, node: Node<'t>, out: &mut Vec<Piece>) -> bool {
+        match node.kind() {
+            "string" => out.push(Piece::Text(unquote(self.text(node)).to_string())),
+            "template_string" => {
+                let mut start = node.start_byte() + 1;
+                let mut cursor = node.walk();
+                for child in node.named_children(&mut cursor) {
+                    if child.kind() != "template_substitution" {
+                        continue;
+                    }
+                    out.push(Piece::Text(
+                        self.source[start..child.start_byte()].to_string(),
+                    ));
+                    let expr = child.named_child(0).unwrap_or(child);
+                    match self.constant(expr) {
+                        Some(value) => out.push(Piece::Text(value)),
+                        None => out.push(Piece::Expr),
+                    }
+                    start = child.end_byte();
+                }
+                let end = node.end_byte().saturating_sub(1).max(start);
+                out.push(Piece::Text(self.source[start..end].to_string()));
+            }
+            "binary_expression"
+                if node
+                    .child_by_field_name("operator")
+                    .is_some_and(|o| self.text(o) == "+") =>
+            {
+                let (Some(left), Some(right)) = (
+                    node.child_by_field_name("left"),
+                    node.child_by_field_name("right"),
+                ) else {
+                    return false;
+                };
+                return self.pieces(left, out) && self.pieces(right, out);
+            }
+            "parenthesized_expression" => {
+                return node.named_child(0).is_some_and(|n| self.pieces(n, out));
+            }
+            "identifier" | "member_expression" => match self.constant(node) {
+                Some(value) => out.push(Piece::Text(value)),
+                None => out.push(Piece::Expr),
+            },
+            "call_expression" | "subscript_expression" | "non_null_expression" => {
+                out.push(Piece::Expr)
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn constant(&self, node: Node) -> Option<String> {
+        (node.kind() == "identifier")
+            .then(|| self.constants.get(self.text(node)).cloned())
+            .flatten()
+    }
+
+    /// What a body expression sends, looking `x` up in its declaration
+    fn body(&self, node: Node, before: usize) -> Option<Body> {
+        match node.kind() {
+            "object" => {
+                let mut fields = Vec::new();
+                let mut cursor = node.walk();
+                for property in node.named_children(&mut cursor) {
+                    match property.kind() {
+                        "pair" => {
+                            let key = property.child_by_field_name("key")?;
+                            if key.kind() == "computed_property_name" {
+                                return None;
+                            }
+                            fields.push(unquote(self.text(key)).to_string());
+                        }
+                        "shorthand_property_identifier" => {
+                            fields.push(self.text(property).to_string())
+                        }
+                        "spread_element" => return None,
+                        _ => {}
+                    }
+                }
+                Some(Body::Fields(fields))
+            }
+            "as_expression" | "satisfies_expression" => {
+                let ty = node.named_child(1)?;
+                match ty.kind() {
+                    "type_identifier" => Some(Body::Type(self.text(ty).to_string())),
+                    _ => None,
+                }
+            }
+            "identifier" => {
+                let name = self.text(node);
+                let declaration = self
+                    .declarations
+                    .iter()
+                    .filter(|d| d.start_byte() < before)
+                    .filter(|d| {
+                        d.child_by_field_name("name")
+                            .or_else(|| d.child_by_field_name("pattern"))
+                            .is_some_and(|n| self.text(n) == name)
+                    })
+                    .max_by_key(|d| d.start_byte())?;
+                let annotated = declaration
+                    .child_by_field_name("type")
+                    .and_then(|t| t.named_child(0))
+                    .filter(|t| t.kind() == "type_identifier");
+                match annotated {
+                    Some(ty) => Some(Body::Type(self.text(ty).to_string())),
+                    None => self.body(declaration.child_by_field_name("value")?, before),
+                }
+            }
+            "parenthesized_expression" => self.body(node.named_child(0)?, before),
+            _ => None,
+        }
+    }
+
+    /// `interface NewUser extends Base { name: string }`,
+    /// `type NewUser = { name: string }`
+    fn interface(&self, node: Node) -> Option<(String, Interface)> {
+        let name = self.text(node.child_by_field_name("name")?).to_string();
+        let body = match node.kind() {
+            "interface_declaration" => node.child_by_field_name("body")?,
+            _ => node
+                .child_by_field_name("value")
+                .filter(|v| v.kind() == "object_type")?,
+        };
+        let mut fields = Vec::new();
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            match member.kind() {
+                "property_signature" => {
+                    let key = member.child_by_field_name("name")?;
+                    fields.push(unquote(self.text(key)).to_string());
+                }
+                // An index signature accepts any field
+                "index_signature" => return None,
+                _ => {}
+            }
+        }
+        let mut extends = Vec::new();
+        let mut cursor = node.walk();
+        for clause in node
+            .named_children(&mut cursor)
+            .filter(|c| c.kind() == "extends_type_clause")
+        {
+            let mut types = clause.walk();
+            for ty in clause.named_children(&mut types) {
+                extends.push(self.text(ty).to_string());
+            }
+        }
+        Some((name, Interface { fields, extends }))
+    }
+}
+
+/// Part of a URL expression
+enum Piece {
+    Text(String),
+    /// Computed at runtime
+    Expr,
+}
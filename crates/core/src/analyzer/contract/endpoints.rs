@@ -0,0 +1,1015 @@
+//! Backend endpoints and the request models they take
+//!
+//! | Framework | Route | Mounted by |
+//! |-----------|-------|------------|
+//! | FastAPI | `@router.get("/{id}")`, `@app.api_route(path, methods=[...])` | `APIRouter(prefix=...)`, `app.include_router(users.router, prefix=...)` |
+//! | Flask | `@bp.route("/<int:id>", methods=[...])`, `@app.post(...)` | `Blueprint(..., url_prefix=...)`, `app.register_blueprint(bp, url_prefix=...)` |
+//! | Express | `router.get("/:id", handler)` | `app.use("/users", router)`, with the router imported or required from another file |
+//!
+//! Routers mounted from another module are followed through its import:
+//! `from app.routers import users` then `include_router(users.router)`, or
+//! `import usersRouter from "./routes/users"` then `app.use(..., usersRouter)`.
+//!
+//! A FastAPI handler's request body is its parameter annotated with a
+//! Pydantic model. A model's fields are its annotated class attributes and
+//! those of the models it derives from; a field is required when it has no
+//! default (or `Field(...)`), and travels under its `alias`, camel-cased
+//! when the model configures a camel-case `alias_generator`.
+
+use crate::analyzer::websec::{descendants, line, text, unquote};
+use crate::parser::grammar::{language_for, Family, Grammar};
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use tree_sitter::Node;
+
+/// Routers mounted through more levels than this are taken as unmounted
+const MAX_DEPTH: usize = 8;
+
+/// HTTP methods a route can be registered with
+const METHODS: &[&str] = &["get", "post", "put", "patch", "delete", "head", "options"];
+
+/// The grammar of `path`, if it is scanned
+pub fn grammar(path: &Path) -> Option<Grammar> {
+    language_for(path).filter(|g| matches!(g.family, Family::Python | Family::Js))
+}
+
+/// A backend route with its full path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    /// Upper case: `GET`
+    pub method: String,
+    /// The path as declared, prefixes included: `/users/{user_id}`
+    pub path: String,
+    /// [`super::template`] of `path`
+    pub template: Vec<String>,
+    /// The handler function: `get_user`
+    pub handler: String,
+    /// The Pydantic model the handler takes as its body
+    pub body: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// A Pydantic model
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Model {
+    pub name: String,
+    pub bases: Vec<String>,
+    pub fields: Vec<Field>,
+    /// Whether field names travel camel-cased (`alias_generator=to_camel`)
+    pub camel: bool,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// A model field under the name it travels as
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub name: String,
+    pub alias: Option<String>,
+    pub required: bool,
+}
+
+/// The fields `model` accepts, its bases' first, named as they travel
+pub fn model_fields(model: &Model, models: &HashMap<String, Model>) -> Vec<Field> {
+    let mut visited = HashSet::new();
+    let (fields, camel) = collect_fields(model, models, &mut visited);
+    fields
+        .into_iter()
+        .map(|f| Field {
+            name: match &f.alias {
+                Some(alias) => alias.clone(),
+                None if camel => to_camel(&f.name),
+                None => f.name.clone(),
+            },
+            ..f
+        })
+        .collect()
+}
+
+fn collect_fields<'m>(
+    model: &'m Model,
+    models: &'m HashMap<String, Model>,
+    visited: &mut HashSet<&'m str>,
+) -> (Vec<Field>, bool) {
+    if !visited.insert(&model.name) {
+        return (Vec::new(), false);
+    }
+    let mut fields: Vec<Field> = Vec::new();
+    let mut camel = model.camel;
+    for base in model.bases.iter().filter_map(|b| models.get(b)) {
+        let (inherited, base_camel) = collect_fields(base, models, visited);
+        fields.extend(inherited);
+        camel |= base_camel;
+    }
+    for field in &model.fields {
+        fields.retain(|f| f.name != field.name);
+        fields.push(field.clone());
+    }
+    (fields, camel)
+}
+
+/// `user_id` → `userId`
+fn to_camel(name: &str) -> String {
+    let mut out = String::new();
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = !out.is_empty();
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Routes, routers and models of every scanned file, resolved together
+#[derive(Debug, Default)]
+pub struct Backend {
+    modules: Vec<Module>,
+}
+
+/// What one file declares
+#[derive(Debug)]
+struct Module {
+    file: PathBuf,
+    /// Path relative to the repository root, without extension, `/`-separated
+    name: String,
+    routers: Vec<Router>,
+    mounts: Vec<Mount>,
+    routes: Vec<Route>,
+    /// Local name → where it is imported from
+    imports: HashMap<String, Import>,
+    models: Vec<Model>,
+}
+
+/// An app, router or blueprint assigned to a name
+#[derive(Debug)]
+struct Router {
+    var: String,
+    prefix: String,
+}
+
+/// `parent.include_router(child, prefix=...)`
+#[derive(Debug)]
+struct Mount {
+    parent: String,
+    child: Child,
+    prefix: String,
+    /// Flask: a `url_prefix` given when registering replaces the blueprint's
+    replaces: bool,
+}
+
+/// The router a mount names
+#[derive(Debug)]
+enum Child {
+    /// A router of the same file
+    Local(String),
+    /// `var` of the Python module `module` (dotted, maybe relative)
+    Module { module: String, var: String },
+    /// Any router of a JavaScript file (relative to the repository root,
+    /// without extension)
+    File(String),
+}
+
+/// Where an imported name comes from
+#[derive(Debug, Clone)]
+enum Import {
+    /// Python: `from module import name`, or `import module as x` when
+    /// `name` is `None`
+    Python {
+        module: String,
+        name: Option<String>,
+    },
+    /// JavaScript: the default export of a file
+    File(String),
+}
+
+/// A route as declared on a router
+#[derive(Debug)]
+struct Route {
+    var: String,
+    method: String,
+    path: String,
+    handler: String,
+    line: usize,
+    /// `(name, annotation)` of the handler's parameters
+    params: Vec<(String, String)>,
+}
+
+impl Backend {
+    /// Add the declarations of `rel` (relative to the repository root)
+    pub fn scan(&mut self, family: Family, file: &Path, rel: &Path, root: Node, source: &str) {
+        let name = rel.with_extension("").to_string_lossy().replace('\\', "/");
+        let mut module = Module {
+            file: file.to_path_buf(),
+            name,
+            routers: Vec::new(),
+            mounts: Vec::new(),
+            routes: Vec::new(),
+            imports: HashMap::new(),
+            models: Vec::new(),
+        };
+        match family {
+            Family::Python => python(&mut module, root, source),
+            _ => js(&mut module, root, source),
+        }
+        if !module.routes.is_empty() || !module.routers.is_empty() || !module.models.is_empty() {
+            self.modules.push(module);
+        }
+    }
+
+    /// Every endpoint with its full path, and the models by name
+    pub fn resolve(self) -> (Vec<Endpoint>, HashMap<String, Model>) {
+        let models: HashMap<String, Model> = self
+            .modules
+            .iter()
+            .flat_map(|m| m.models.iter().cloned())
+            .map(|m| (m.name.clone(), m))
+            .collect();
+        let pydantic = pydantic_models(&models);
+        let mut endpoints = Vec::new();
+        for (index, module) in self.modules.iter().enumerate() {
+            for route in &module.routes {
+                let path_params: Vec<String> = super::template(&route.path)
+                    .iter()
+                    .zip(route.path.split('/').filter(|s| !s.is_empty()))
+                    .filter(|(t, _)| *t == "{}")
+                    .map(|(_, s)| param_name(s))
+                    .collect();
+                let body = route
+                    .params
+                    .iter()
+                    .find(|(name, annotation)| {
+                        pydantic.contains(annotation) && !path_params.contains(name)
+                    })
+                    .map(|(_, annotation)| annotation.clone());
+                for prefix in self.prefixes(index, &route.var, 0) {
+                    let path = join(&[&prefix, &route.path]);
+                    endpoints.push(Endpoint {
+                        method: route.method.clone(),
+                        template: super::template(&path),
+                        path,
+                        handler: route.handler.clone(),
+                        body: body.clone(),
+                        file: module.file.clone(),
+                        line: route.line,
+                    });
+                }
+            }
+        }
+        endpoints.dedup_by(|a, b| a.method == b.method && a.path == b.path && a.file == b.file);
+        let models = models
+            .into_iter()
+            .filter(|(name, _)| pydantic.contains(name))
+            .collect();
+        (endpoints, models)
+    }
+
+    /// The prefixes router `var` of module `index` is reached under
+    fn prefixes(&self, index: usize, var: &str, depth: usize) -> Vec<String> {
+        let module = &self.modules[index];
+        let own = module
+            .routers
+            .iter()
+            .find(|r| r.var == var)
+            .map_or("", |r| r.prefix.as_str());
+        if depth >= MAX_DEPTH {
+            return vec![own.to_string()];
+        }
+        // A router imported from another module is that module's router
+        if !module.routers.iter().any(|r| r.var == var) {
+            if let Some(Import::Python {
+                module: from,
+                name: Some(name),
+            }) = module.imports.get(var)
+            {
+                if let Some(target) = self.find_module(from) {
+                    return self.prefixes(target, name, depth + 1);
+                }
+            }
+        }
+        let mut prefixes = Vec::new();
+        for (parent_index, parent) in self.modules.iter().enumerate() {
+            for mount in &parent.mounts {
+                if !self.mounts(parent_index, &mount.child, index, var) {
+                    continue;
+                }
+                let own = if mount.replaces && !mount.prefix.is_empty() {
+                    ""
+                } else {
+                    own
+                };
+                for outer in self.prefixes(parent_index, &mount.parent, depth + 1) {
+                    prefixes.push(join(&[&outer, &mount.prefix, own]));
+                }
+            }
+        }
+        if prefixes.is_empty() {
+            prefixes.push(own.to_string());
+        }
+        prefixes
+    }
+
+    /// Whether `child`, mounted in module `from`, is router `var` of
+    /// module `index`
+    fn mounts(&self, from: usize, child: &Child, index: usize, var: &str) -> bool {
+        match child {
+            Child::Local(name) => from == index && name == var,
+            Child::Module { module, var: name } => {
+                name == var && python_module_is(&self.modules[index].name, module)
+            }
+            Child::File(path) => {
+                let name = &self.modules[index].name;
+                name == path || *name == format!("{}/index", path)
+            }
+        }
+    }
+
+    fn find_module(&self, module: &str) -> Option<usize> {
+        self.modules
+            .iter()
+            .position(|m| python_module_is(&m.name, module))
+    }
+}
+
+/// Whether the file `name` (relative, without extension) is the Python
+/// module `module` (`app.routers.users`, or relative: `.routers.users`)
+fn python_module_is(name: &str, module: &str) -> bool {
+    let dotted = module.trim_start_matches('.').replace('.', "/");
+    if dotted.is_empty() {
+        return false;
+    }
+    [dotted.clone(), format!("{}/__init__", dotted)]
+        .iter()
+        .any(|m| name == m || name.ends_with(&format!("/{}", m)))
+}
+
+/// Names of the models deriving from `BaseModel`, directly or not
+fn pydantic_models(models: &HashMap<String, Model>) -> HashSet<String> {
+    let mut found: HashSet<String> = HashSet::new();
+    loop {
+        let before = found.len();
+        for model in models.values() {
+            if model
+                .bases
+                .iter()
+                .any(|b| matches!(b.as_str(), "BaseModel" | "SQLModel") || found.contains(b))
+            {
+                found.insert(model.name.clone());
+            }
+        }
+        if found.len() == before {
+            return found;
+        }
+    }
+}
+
+/// The name of a parameter segment: `{user_id}`, `<int:user_id>`, `:id`
+fn param_name(segment: &str) -> String {
+    let inner = segment
+        .trim_start_matches([':', '{', '<'])
+        .trim_end_matches(['}', '>', '?']);
+    let inner = inner.rsplit_once(':').map_or(inner, |(kind, name)| {
+        // `{path:path}` names first, `<int:id>` last
+        if segment.starts_with('{') {
+            kind
+        } else {
+            name
+        }
+    });
+    inner.to_string()
+}
+
+/// Path pieces joined with single slashes: `/users` + `/{id}` → `/users/{id}`
+fn join(parts: &[&str]) -> String {
+    let joined = parts
+        .iter()
+        .flat_map(|p| p.split('/'))
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("/{}", joined)
+}
+
+/// A string literal's content; `None` for f-strings and other expressions
+fn literal(node: Node, source: &str) -> Option<String> {
+    let interpolated = descendants(node)
+        .iter()
+        .any(|n| matches!(n.kind(), "interpolation" | "template_substitution"));
+    (matches!(node.kind(), "string" | "template_string") && !interpolated)
+        .then(|| unquote(text(node, source)).to_string())
+}
+
+/// The `name=value` keyword arguments and the positional ones of a call
+fn arguments<'t>(call: Node<'t>, source: &str) -> (Vec<Node<'t>>, HashMap<String, Node<'t>>) {
+    let mut positional = Vec::new();
+    let mut keywords = HashMap::new();
+    if let Some(list) = call.child_by_field_name("arguments") {
+        let mut cursor = list.walk();
+        for argument in list.named_children(&mut cursor) {
+            match argument.kind() {
+                "keyword_argument" => {
+                    if let (Some(name), Some(value)) = (
+                        argument.child_by_field_name("name"),
+                        argument.child_by_field_name("value"),
+                    ) {
+                        keywords.insert(text(name, source).to_string(), value);
+                    }
+                }
+                "comment" => {}
+                _ => positional.push(argument),
+            }
+        }
+    }
+    (positional, keywords)
+}
+
+// ── Python ────────────────────────────────────────────────────────────────────
+
+fn python(module: &mut Module, root: Node, source: &str) {
+    for node in descendants(root) {
+        match node.kind() {
+            "import_from_statement" => python_from_import(module, node, source),
+            "import_statement" => {
+                let mut cursor = node.walk();
+                for name in node.children_by_field_name("name", &mut cursor) {
+                    if name.kind() == "aliased_import" {
+                        if let (Some(dotted), Some(alias)) = (
+                            name.child_by_field_name("name"),
+                            name.child_by_field_name("alias"),
+                        ) {
+                            module.imports.insert(
+                                text(alias, source).to_string(),
+                                Import::Python {
+                                    module: text(dotted, source).to_string(),
+                                    name: None,
+                                },
+                            );
+                        }
+                    } else {
+                        let dotted = text(name, source).to_string();
+                        module.imports.insert(
+                            dotted.clone(),
+                            Import::Python {
+                                module: dotted,
+                                name: None,
+                            },
+                        );
+                    }
+                }
+            }
+            "assignment" => python_router(module, node, source),
+            "call" => python_mount(module, node, source),
+            "decorated_definition" => python_route(module, node, source),
+            "class_definition" => {
+                if let Some(model) = python_model(&module.file, node, source) {
+                    module.models.push(model);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `from app.routers import users`, `from .users import router as users`
+fn python_from_import(module: &mut Module, node: Node, source: &str) {
+    let Some(from) = node.child_by_field_name("module_name") else {
+        return;
+    };
+    let from = text(from, source).to_string();
+    let mut cursor = node.walk();
+    for name in node.children_by_field_name("name", &mut cursor) {
+        let (imported, local) = match name.kind() {
+            "aliased_import" => (
+                name.child_by_field_name("name"),
+                name.child_by_field_name("alias"),
+            ),
+            _ => (Some(name), Some(name)),
+        };
+        if let (Some(imported), Some(local)) = (imported, local) {
+            module.imports.insert(
+                text(local, source).to_string(),
+                Import::Python {
+                    module: from.clone(),
+                    name: Some(text(imported, source).to_string()),
+                },
+            );
+        }
+    }
+}
+
+/// `router = APIRouter(prefix="/users")`, `bp = Blueprint(..., url_prefix=...)`,
+/// `app = FastAPI()`
+fn python_router(module: &mut Module, node: Node, source: &str) {
+    let (Some(left), Some(right)) = (
+        node.child_by_field_name("left"),
+        node.child_by_field_name("right"),
+    ) else {
+        return;
+    };
+    if left.kind() != "identifier" || right.kind() != "call" {
+        return;
+    }
+    let Some(function) = right.child_by_field_name("function") else {
+        return;
+    };
+    let callee = text(function, source);
+    let callee = callee.rsplit('.').next().unwrap_or(callee);
+    let prefix_keyword = match callee {
+        "APIRouter" => "prefix",
+        "Blueprint" => "url_prefix",
+        "FastAPI" | "Flask" => "",
+        _ => return,
+    };
+    let (_, keywords) = arguments(right, source);
+    let prefix = keywords
+        .get(prefix_keyword)
+        .and_then(|v| literal(*v, source))
+        .unwrap_or_default();
+    module.routers.push(Router {
+        var: text(left, source).to_string(),
+        prefix,
+    });
+}
+
+/// `app.include_router(users.router, prefix="/users")`,
+/// `app.register_blueprint(bp, url_prefix="/users")`
+fn python_mount(module: &mut Module, call: Node, source: &str) {
+    let Some(function) = call
+        .child_by_field_name("function")
+        .filter(|f| f.kind() == "attribute")
+    else {
+        return;
+    };
+    let (Some(parent), Some(method)) = (
+        function.child_by_field_name("object"),
+        function.child_by_field_name("attribute"),
+    ) else {
+        return;
+    };
+    let (prefix_keyword, replaces) = match text(method, source) {
+        "include_router" => ("prefix", false),
+        "register_blueprint" => ("url_prefix", true),
+        _ => return,
+    };
+    let (positional, keywords) = arguments(call, source);
+    let Some(child) = positional
+        .first()
+        .copied()
+        .or_else(|| keywords.get("router").copied())
+        .or_else(|| keywords.get("blueprint").copied())
+    else {
+        return;
+    };
+    let child = match child.kind() {
+        "identifier" => {
+            let name = text(child, source);
+            match module.imports.get(name) {
+                Some(Import::Python {
+                    module: from,
+                    name: Some(imported),
+                }) => Child::Module {
+                    module: from.clone(),
+                    var: imported.clone(),
+                },
+                _ => Child::Local(name.to_string()),
+            }
+        }
+        "attribute" => {
+            let (Some(object), Some(attribute)) = (
+                child.child_by_field_name("object"),
+                child.child_by_field_name("attribute"),
+            ) else {
+                return;
+            };
+            let module_path = match module.imports.get(text(object, source)) {
+                Some(Import::Python {
+                    module: from,
+                    name: Some(name),
+                }) => format!("{}.{}", from.trim_end_matches('.'), name),
+                Some(Import::Python { module: from, .. }) => from.clone(),
+                _ => text(object, source).to_string(),
+            };
+            Child::Module {
+                module: module_path,
+                var: text(attribute, source).to_string(),
+            }
+        }
+        _ => return,
+    };
+    module.mounts.push(Mount {
+        parent: text(parent, source).to_string(),
+        child,
+        prefix: keywords
+            .get(prefix_keyword)
+            .and_then(|v| literal(*v, source))
+            .unwrap_or_default(),
+        replaces,
+    });
+}
+
+/// `@router.get("/{id}") def get_user(id: int): ...`
+fn python_route(module: &mut Module, node: Node, source: &str) {
+    let Some(function) = node
+        .child_by_field_name("definition")
+        .filter(|d| matches!(d.kind(), "function_definition"))
+    else {
+        return;
+    };
+    let handler = function
+        .child_by_field_name("name")
+        .map_or("", |n| text(n, source))
+        .to_string();
+    let params = function
+        .child_by_field_name("parameters")
+        .map(|p| python_params(p, source))
+        .unwrap_or_default();
+    let mut cursor = node.walk();
+    let decorators: Vec<Node> = node
+        .named_children(&mut cursor)
+        .filter(|c| c.kind() == "decorator")
+        .collect();
+    for decorator in decorators {
+        let Some(call) = descendants(decorator)
+            .into_iter()
+            .find(|n| n.kind() == "call")
+        else {
+            continue;
+        };
+        let Some(function) = call
+            .child_by_field_name("function")
+            .filter(|f| f.kind() == "attribute")
+        else {
+            continue;
+        };
+        let (Some(object), Some(attribute)) = (
+            function.child_by_field_name("object"),
+            function.child_by_field_name("attribute"),
+        ) else {
+            continue;
+        };
+        if object.kind() != "identifier" {
+            continue;
+        }
+        let (positional, keywords) = arguments(call, source);
+        let Some(path) = positional
+            .first()
+            .or_else(|| keywords.get("path"))
+            .or_else(|| keywords.get("rule"))
+            .and_then(|p| literal(*p, source))
+        else {
+            continue;
+        };
+        let attribute = text(attribute, source);
+        let methods: Vec<String> = if METHODS.contains(&attribute) {
+            vec![attribute.to_uppercase()]
+        } else if matches!(attribute, "route" | "api_route") {
+            match keywords.get("methods") {
+                Some(list) => {
+                    let mut cursor = list.walk();
+                    list.named_children(&mut cursor)
+                        .filter_map(|m| literal(m, source))
+                        .map(|m| m.to_uppercase())
+                        .collect()
+                }
+                None => vec!["GET".to_string()],
+            }
+        } else {
+            continue;
+        };
+        for method in methods {
+            module.routes.push(Route {
+                var: text(object, source).to_string(),
+                method,
+                path: path.clone(),
+                handler: handler.clone(),
+                line: line(node),
+                params: params.clone(),
+            });
+        }
+    }
+}
+
+/// `(name, annotation)` of the annotated parameters that may be a body:
+/// not `Depends(...)`, `Query(...)` and the like
+fn python_params(parameters: Node, source: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut cursor = parameters.walk();
+    for param in parameters.named_children(&mut cursor) {
+        let name = match param.kind() {
+            "typed_parameter" => param.named_child(0),
+            "typed_default_parameter" => {
+                let injected = param.child_by_field_name("value").is_some_and(|v| {
+                    let value = text(v, source);
+                    ["Depends", "Query", "Path", "Header", "Cookie", "Security"]
+                        .iter()
+                        .any(|f| value.starts_with(f))
+                });
+                if injected {
+                    continue;
+                }
+                param.child_by_field_name("name")
+            }
+            _ => continue,
+        };
+        if let (Some(name), Some(annotation)) = (name, param.child_by_field_name("type")) {
+            params.push((
+                text(name, source).to_string(),
+                text(annotation, source).to_string(),
+            ));
+        }
+    }
+    params
+}
+
+/// `class UserCreate(BaseModel): name: str; email: str = Field(..., alias="mail")`
+///
+/// Any class with annotated attributes is kept; [`pydantic_models`] later
+/// keeps the ones deriving from `BaseModel`.
+fn python_model(file: &Path, class: Node, source: &str) -> Option<Model> {
+    let name = text(class.child_by_field_name("name")?, source).to_string();
+    let bases: Vec<String> = class
+        .child_by_field_name("superclasses")
+        .map(|list| {
+            let mut cursor = list.walk();
+            list.named_children(&mut cursor)
+                .filter(|b| matches!(b.kind(), "identifier" | "attribute"))
+                .map(|b| {
+                    let base = text(b, source);
+                    base.rsplit('.').next().unwrap_or(base).to_string()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if bases.is_empty() {
+        return None;
+    }
+    let body = class.child_by_field_name("body")?;
+    let body_text = text(body, source).to_lowercase();
+    let camel = body_text.contains("alias_generator") && body_text.contains("camel");
+    let mut fields = Vec::new();
+    let mut cursor = body.walk();
+    for statement in body.named_children(&mut cursor) {
+        let Some(assignment) = statement
+            .named_child(0)
+            .filter(|a| statement.kind() == "expression_statement" && a.kind() == "assignment")
+        else {
+            continue;
+        };
+        let (Some(left), Some(annotation)) = (
+            assignment.child_by_field_name("left"),
+            assignment.child_by_field_name("type"),
+        ) else {
+            continue;
+        };
+        let field = text(left, source);
+        if left.kind() != "identifier"
+            || field.starts_with('_')
+            || field == "model_config"
+            || text(annotation, source).starts_with("ClassVar")
+        {
+            continue;
+        }
+        let (required, alias) = match assignment.child_by_field_name("right") {
+            None => (true, None),
+            Some(value) if value.kind() == "call" => {
+                let callee = value
+                    .child_by_field_name("function")
+                    .map_or("", |f| text(f, source));
+                if callee.ends_with("Field") {
+                    let (positional, keywords) = arguments(value, source);
+                    let required = match positional.first() {
+                        Some(first) => first.kind() == "ellipsis",
+                        None => {
+                            !keywords.contains_key("default")
+                                && !keywords.contains_key("default_factory")
+                        }
+                    };
+                    let alias = keywords
+                        .get("alias")
+                        .or_else(|| keywords.get("validation_alias"))
+                        .and_then(|a| literal(*a, source));
+                    (required, alias)
+                } else {
+                    (false, None)
+                }
+            }
+            Some(_) => (false, None),
+        };
+        fields.push(Field {
+            name: field.to_string(),
+            alias,
+            required,
+        });
+    }
+    Some(Model {
+        name,
+        bases,
+        fields,
+        camel,
+        file: file.to_path_buf(),
+        line: line(class),
+    })
+}
+
+// ── JavaScript / TypeScript ───────────────────────────────────────────────────
+
+fn js(module: &mut Module, root: Node, source: &str) {
+    let dir = Path::new(&module.name)
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    for node in descendants(root) {
+        match node.kind() {
+            "import_statement" => {
+                let (Some(from), Some(clause)) = (
+                    node.child_by_field_name("source")
+                        .and_then(|s| literal(s, source)),
+                    descendants(node)
+                        .into_iter()
+                        .find(|n| n.kind() == "import_clause"),
+                ) else {
+                    continue;
+                };
+                let Some(default) = clause.named_child(0).filter(|n| n.kind() == "identifier")
+                else {
+                    continue;
+                };
+                if let Some(path) = relative_import(&dir, &from) {
+                    module
+                        .imports
+                        .insert(text(default, source).to_string(), Import::File(path));
+                }
+            }
+            "variable_declarator" => js_declarator(module, &dir, node, source),
+            "call_expression" => js_call(module, &dir, node, source),
+            _ => {}
+        }
+    }
+}
+
+/// `from` resolved against `dir`, for `./` and `../` specifiers
+fn relative_import(dir: &Path, from: &str) -> Option<String> {
+    if !from.starts_with('.') {
+        return None;
+    }
+    let mut path = dir.to_path_buf();
+    for component in Path::new(from).components() {
+        match component {
+            Component::ParentDir => {
+                path.pop();
+            }
+            Component::Normal(part) => path.push(part),
+            _ => {}
+        }
+    }
+    let path = path.to_string_lossy().replace('\\', "/");
+    let path = [".js", ".ts", ".mjs", ".cjs"]
+        .iter()
+        .find_map(|ext| path.strip_suffix(ext))
+        .unwrap_or(&path);
+    Some(path.to_string())
+}
+
+/// `const router = express.Router()`, `const app = express()`,
+/// `const users = require("./routes/users")`
+fn js_declarator(module: &mut Module, dir: &Path, node: Node, source: &str) {
+    let (Some(name), Some(value)) = (
+        node.child_by_field_name("name")
+            .filter(|n| n.kind() == "identifier"),
+        node.child_by_field_name("value"),
+    ) else {
+        return;
+    };
+    let name = text(name, source).to_string();
+    let callee = match value.kind() {
+        "call_expression" => value.child_by_field_name("function"),
+        "new_expression" => value.child_by_field_name("constructor"),
+        _ => None,
+    };
+    let Some(callee) = callee else {
+        return;
+    };
+    match text(callee, source) {
+        "express" | "express.Router" | "Router" | "fastify" | "Fastify" => {
+            module.routers.push(Router {
+                var: name,
+                prefix: String::new(),
+            })
+        }
+        "require" => {
+            let (positional, _) = arguments(value, source);
+            if let Some(path) = positional
+                .first()
+                .and_then(|p| literal(*p, source))
+                .and_then(|from| relative_import(dir, &from))
+            {
+                module.imports.insert(name, Import::File(path));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `router.get("/users/:id", handler)`, `app.use("/users", usersRouter)`
+fn js_call(module: &mut Module, dir: &Path, call: Node, source: &str) {
+    let Some(function) = call
+        .child_by_field_name("function")
+        .filter(|f| f.kind() == "member_expression")
+    else {
+        return;
+    };
+    let (Some(object), Some(property)) = (
+        function
+            .child_by_field_name("object")
+            .filter(|o| o.kind() == "identifier"),
+        function.child_by_field_name("property"),
+    ) else {
+        return;
+    };
+    let var = text(object, source).to_string();
+    let method = text(property, source);
+    let (positional, _) = arguments(call, source);
+    if method == "use" {
+        let (prefix, child) = match positional.as_slice() {
+            [prefix, child, ..] => match literal(*prefix, source) {
+                Some(prefix) => (prefix, *child),
+                None => return,
+            },
+            [child] => (String::new(), *child),
+            [] => return,
+        };
+        let child = match child.kind() {
+            "identifier" => {
+                let name = text(child, source);
+                match module.imports.get(name) {
+                    Some(Import::File(path)) => Child::File(path.clone()),
+                    _ => Child::Local(name.to_string()),
+                }
+            }
+            "call_expression"
+                if child
+                    .child_by_field_name("function")
+                    .is_some_and(|f| text(f, source) == "require") =>
+            {
+                let (arguments, _) = arguments(child, source);
+                match arguments
+                    .first()
+                    .and_then(|p| literal(*p, source))
+                    .and_then(|from| relative_import(dir, &from))
+                {
+                    Some(path) => Child::File(path),
+                    None => return,
+                }
+            }
+            _ => return,
+        };
+        module.mounts.push(Mount {
+            parent: var,
+            child,
+            prefix,
+            replaces: false,
+        });
+        return;
+    }
+    let methods: Vec<String> = match method {
+        "all" => METHODS.iter().map(|m| m.to_uppercase()).collect(),
+        m if METHODS.contains(&m) => vec![m.to_uppercase()],
+        _ => return,
+    };
+    let (Some(path), Some(last)) = (
+        positional.first().and_then(|p| literal(*p, source)),
+        positional.last(),
+    ) else {
+        return;
+    };
+    if positional.len() < 2 || !path.starts_with('/') {
+        return;
+    }
+    // A server route: a known app or router, or a handler function
+    let handler_function = matches!(
+        last.kind(),
+        "arrow_function" | "function_expression" | "function"
+    );
+    if !handler_function && !module.routers.iter().any(|r| r.var == var) {
+        return;
+    }
+    let handler = match last.kind() {
+        "identifier" | "member_expression" => text(*last, source).to_string(),
+        _ => format!("{}.{}", var, method),
+    };
+    for method in methods {
+        module.routes.push(Route {
+            var: var.clone(),
+            method,
+            path: path.clone(),
+            handler: handler.clone(),
+            line: line(call),
+            params: Vec::new(),
+        });
+    }
+}
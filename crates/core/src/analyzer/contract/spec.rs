@@ -0,0 +1,102 @@
+//! Operations of the OpenAPI specs generated clients are built from
+//!
+//! Each `[[analyzers.generated]]` spec that is an OpenAPI document (JSON or
+//! YAML) lists its operations under `paths`; an operation with an
+//! `operationId` is what a generated client's method of that name calls.
+//! Other specs (`.proto`, ...) have no HTTP operations and are skipped.
+
+use crate::analyzer::kubernetes::yaml::{parse_documents, Node};
+use crate::config::GeneratedCodeConfig;
+use std::path::Path;
+
+/// HTTP methods an OpenAPI path item can hold
+const METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch"];
+
+/// An OpenAPI operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Operation {
+    pub id: String,
+    /// Upper case: `GET`
+    pub method: String,
+    pub path: String,
+}
+
+/// `operationId` as generators name methods after it: `create_user`,
+/// `createUser` and `CreateUser` are the same key
+pub fn operation_key(id: &str) -> String {
+    id.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Operations of every configured spec
+pub fn operations(generated: &[GeneratedCodeConfig], repo_root: &Path) -> Vec<Operation> {
+    let mut operations = Vec::new();
+    for entry in generated {
+        let path = repo_root.join(&entry.spec);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => {
+                if let Ok(document) = serde_json::from_str::<serde_json::Value>(&content) {
+                    operations.extend(from_json(&document));
+                }
+            }
+            Some("yaml" | "yml") => {
+                for document in parse_documents(&content) {
+                    operations.extend(from_yaml(&document));
+                }
+            }
+            _ => {}
+        }
+    }
+    operations
+}
+
+fn from_json(document: &serde_json::Value) -> Vec<Operation> {
+    let Some(paths) = document.get("paths").and_then(|p| p.as_object()) else {
+        return Vec::new();
+    };
+    let mut operations = Vec::new();
+    for (path, item) in paths {
+        for method in METHODS {
+            if let Some(id) = item
+                .get(*method)
+                .and_then(|o| o.get("operationId"))
+                .and_then(|i| i.as_str())
+            {
+                operations.push(Operation {
+                    id: id.to_string(),
+                    method: method.to_uppercase(),
+                    path: path.clone(),
+                });
+            }
+        }
+    }
+    operations
+}
+
+fn from_yaml(document: &Node) -> Vec<Operation> {
+    let Some(paths) = document.get("paths") else {
+        return Vec::new();
+    };
+    let mut operations = Vec::new();
+    for entry in paths.entries() {
+        for method in METHODS {
+            if let Some(id) = entry
+                .value
+                .path(&[method, "operationId"])
+                .and_then(Node::as_str)
+            {
+                operations.push(Operation {
+                    id: id.to_string(),
+                    method: method.to_uppercase(),
+                    path: entry.key.clone(),
+                });
+            }
+        }
+    }
+    operations
+}
@@ -0,0 +1,392 @@
+//! Stale-copy analyzer — user-facing text that dates a product: old
+//! copyright years, references to long-past versions and retired names
+//!
+//! Only string literals are read from Python, JavaScript/TypeScript, Ruby,
+//! PHP, Rust and Go files, so identifiers and comments that contain a term
+//! are never reported; Markdown and HTML files are read whole (see
+//! [`text`]):
+//!
+//! - `copyright_year`: `©`, `(c)` or `Copyright` followed by a year, or a
+//!   range ending in one, before the year of the latest commit (the current
+//!   year outside a repository). Fixable when the notice appears once on its
+//!   line
+//! - `version_reference`: `version X.Y` or `vX.Y` more than `version_lag`
+//!   major versions behind the nearest manifest's version (see
+//!   [`manifest`]). Medium confidence: the version may be another product's
+//! - `banned_term`: a `[[analyzers.copy.banned]]` term as a whole word.
+//!   Fixable when it has a replacement and appears once on its line
+//!
+//! Files matching `allow_paths` (changelogs and release notes by default)
+//! record history and are skipped.
+//!
+//! Finding prefix: `COPY-`
+
+pub mod manifest;
+pub mod text;
+
+use crate::analyzer::{catalog_finding, Analyzer};
+use crate::config::{CopyConfig, RevetConfig};
+use crate::finding::{Confidence, Finding, FixKind, Severity};
+use crate::msg;
+use manifest::Versions;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Module name of the stale-copy analyzer
+pub const MODULE: &str = "copy";
+
+/// Sub-rules of the stale-copy analyzer
+pub const RULES: &[&str] = &["copyright_year", "version_reference", "banned_term"];
+
+/// `© 2019`, `(c) 2019-2023`, `Copyright © 2019 – 2023`; the last year is
+/// group `year`
+fn copyright() -> &'static Regex {
+    static COPYRIGHT: OnceLock<Regex> = OnceLock::new();
+    COPYRIGHT.get_or_init(|| {
+        Regex::new(
+            r"(?i)(?:©|\(c\)|&copy;|\bcopyright\b)(?:\s*(?:©|\(c\)|&copy;))?\s*(?:\d{4}\s*(?:-|–|—|to)\s*)?(?P<year>\d{4})\b",
+        )
+        .expect("valid copyright regex")
+    })
+}
+
+/// `version 3.2`, `Version 3.2.1`, `v3.2`
+fn version_reference() -> &'static Regex {
+    static VERSION: OnceLock<Regex> = OnceLock::new();
+    VERSION.get_or_init(|| {
+        Regex::new(r"(?i)\b(?:version\s+|v)(?P<major>\d+)\.\d+(?:\.\d+)*\b")
+            .expect("valid version regex")
+    })
+}
+
+/// A compiled `[[analyzers.copy.banned]]` entry
+struct Banned {
+    term: String,
+    pattern: Regex,
+    replacement: Option<String>,
+    severity: Severity,
+}
+
+/// Analyzer that reports stale user-facing copy
+pub struct CopyAnalyzer {
+    version_lag: u64,
+    allow_paths: Vec<glob::Pattern>,
+    banned: Vec<Banned>,
+    year: Option<i64>,
+    copyright_year: Severity,
+    version_reference: Severity,
+}
+
+fn parse_severity(severity: Option<&str>) -> Option<Severity> {
+    match severity? {
+        "error" => Some(Severity::Error),
+        "warning" => Some(Severity::Warning),
+        "info" => Some(Severity::Info),
+        _ => None,
+    }
+}
+
+/// `term` as a whole word; word boundaries only where the term starts or
+/// ends with a word character
+fn whole_word(term: &str) -> Option<Regex> {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let start = if is_word(term.chars().next()) {
+        r"\b"
+    } else {
+        ""
+    };
+    let end = if is_word(term.chars().last()) {
+        r"\b"
+    } else {
+        ""
+    };
+    Regex::new(&format!("{}{}{}", start, regex::escape(term), end)).ok()
+}
+
+/// `text` as a regex replacement that inserts it literally
+fn literal_replacement(text: &str) -> String {
+    text.replace('$', "$$")
+}
+
+/// The year of the latest commit, or the current year outside a repository
+fn reference_year(repo_root: &Path) -> i64 {
+    let committed = git2::Repository::discover(repo_root)
+        .ok()
+        .and_then(|repo| Some(repo.head().ok()?.peel_to_commit().ok()?.time().seconds()));
+    let secs = committed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64)
+    });
+    crate::sbom::rfc3339(secs)[..4].parse().unwrap_or(0)
+}
+
+/// 1-based line and character column of byte `offset` in `content`
+fn position(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// The line of `content` containing byte `offset`
+fn line_at(content: &str, offset: usize) -> &str {
+    let start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let end = content[offset..]
+        .find('\n')
+        .map_or(content.len(), |i| offset + i);
+    &content[start..end]
+}
+
+/// A match found in the text of a file, before it becomes a finding
+struct Hit {
+    start: usize,
+    end: usize,
+    rule: &'static str,
+    severity: Severity,
+    confidence: Confidence,
+    message: crate::i18n::Msg,
+    suggestion: crate::i18n::Msg,
+    fix: Option<FixKind>,
+}
+
+impl CopyAnalyzer {
+    /// Create a stale-copy analyzer with the default settings
+    pub fn new() -> Self {
+        Self::with_config(&CopyConfig::default())
+    }
+
+    /// Create a stale-copy analyzer honouring `[analyzers.copy]`
+    pub fn with_config(config: &CopyConfig) -> Self {
+        let severity = |rule: &str, default: Severity| {
+            parse_severity(config.severity.get(rule).map(String::as_str)).unwrap_or(default)
+        };
+        let banned_default = severity("banned_term", Severity::Warning);
+        Self {
+            version_lag: config.version_lag,
+            allow_paths: config
+                .allow_paths
+                .iter()
+                .filter_map(|p| glob::Pattern::new(p).ok())
+                .collect(),
+            banned: config
+                .banned
+                .iter()
+                .filter(|b| !b.term.trim().is_empty())
+                .filter_map(|b| {
+                    Some(Banned {
+                        term: b.term.clone(),
+                        pattern: whole_word(&b.term)?,
+                        replacement: b.replacement.clone(),
+                        severity: parse_severity(b.severity.as_deref()).unwrap_or(banned_default),
+                    })
+                })
+                .collect(),
+            year: None,
+            copyright_year: severity("copyright_year", Severity::Warning),
+            version_reference: severity("version_reference", Severity::Info),
+        }
+    }
+
+    /// Check copyright notices against `year` instead of the year of the
+    /// latest commit
+    pub fn with_year(mut self, year: i64) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    fn allowed(&self, path: &Path, repo_root: &Path) -> bool {
+        let rel = path.strip_prefix(repo_root).unwrap_or(path);
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        self.allow_paths.iter().any(|p| p.matches(&rel))
+    }
+
+    fn scan(&self, path: &Path, content: &str, year: i64, versions: &mut Versions) -> Vec<Finding> {
+        let spans = text::spans(path, content);
+        if spans.is_empty() {
+            return Vec::new();
+        }
+        let current = versions.of(path);
+
+        let mut hits = Vec::new();
+        for span in &spans {
+            for caps in copyright().captures_iter(&span.text) {
+                let whole = caps.get(0).expect("match");
+                let found = &caps["year"];
+                if found.parse::<i64>().map_or(true, |y| y >= year || y < 1900) {
+                    continue;
+                }
+                let start = span.start + whole.start();
+                let notice = whole.as_str();
+                let year_at = caps.name("year").expect("year").start() - whole.start();
+                let updated = format!("{}{}", &notice[..year_at], year);
+                hits.push(Hit {
+                    start,
+                    end: span.start + whole.end(),
+                    rule: "copyright_year",
+                    severity: self.copyright_year,
+                    confidence: Confidence::High,
+                    message: msg!("copy-copyright-year", year = found, current = year),
+                    suggestion: msg!("copy-copyright-year.suggestion", current = year),
+                    fix: (line_at(content, start).matches(notice).count() == 1).then(|| {
+                        FixKind::ReplacePattern {
+                            find: regex::escape(notice),
+                            replace: literal_replacement(&updated),
+                        }
+                    }),
+                });
+            }
+
+            if let Some(current) = &current {
+                for caps in version_reference().captures_iter(&span.text) {
+                    let whole = caps.get(0).expect("match");
+                    let Ok(major) = caps["major"].parse::<u64>() else {
+                        continue;
+                    };
+                    if major + self.version_lag >= current.major {
+                        continue;
+                    }
+                    let mentioned = whole
+                        .as_str()
+                        .trim_start_matches(|c: char| c.is_alphabetic() || c.is_whitespace());
+                    hits.push(Hit {
+                        start: span.start + whole.start(),
+                        end: span.start + whole.end(),
+                        rule: "version_reference",
+                        severity: self.version_reference,
+                        confidence: Confidence::Medium,
+                        message: msg!(
+                            "copy-version-reference",
+                            version = mentioned,
+                            behind = current.major - major,
+                            current = &current.text
+                        ),
+                        suggestion: msg!("copy-version-reference.suggestion"),
+                        fix: None,
+                    });
+                }
+            }
+
+            for banned in &self.banned {
+                for found in banned.pattern.find_iter(&span.text) {
+                    let start = span.start + found.start();
+                    let once = banned.pattern.find_iter(line_at(content, start)).count() == 1;
+                    let (suggestion, fix) = match &banned.replacement {
+                        Some(replacement) => (
+                            msg!(
+                                "copy-banned-term.suggestion",
+                                replacement = replacement.as_str()
+                            ),
+                            once.then(|| FixKind::ReplacePattern {
+                                find: banned.pattern.as_str().to_string(),
+                                replace: literal_replacement(replacement),
+                            }),
+                        ),
+                        None => (
+                            msg!("copy-banned-term.reword", term = banned.term.as_str()),
+                            None,
+                        ),
+                    };
+                    hits.push(Hit {
+                        start,
+                        end: span.start + found.end(),
+                        rule: "banned_term",
+                        severity: banned.severity,
+                        confidence: Confidence::High,
+                        message: msg!("copy-banned-term", term = banned.term.as_str()),
+                        suggestion,
+                        fix,
+                    });
+                }
+            }
+        }
+
+        hits.sort_by_key(|h| h.start);
+        hits.into_iter()
+            .map(|hit| {
+                let (line, column) = position(content, hit.start);
+                let (end_line, end_column) = position(content, hit.end);
+                Finding {
+                    rule: Some(hit.rule.to_string()),
+                    confidence: hit.confidence,
+                    column: Some(column),
+                    end_column: (end_line == line).then_some(end_column),
+                    ..catalog_finding(
+                        hit.severity,
+                        hit.message,
+                        path.to_path_buf(),
+                        line,
+                        Some(hit.suggestion),
+                        hit.fix,
+                    )
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for CopyAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer for CopyAnalyzer {
+    fn name(&self) -> &str {
+        "Stale copy"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "COPY"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.copy
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some(MODULE)
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        text::accepts(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
+    fn extra_extensions(&self) -> &[&str] {
+        text::DOT_EXTENSIONS
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], repo_root: &Path) -> Vec<Finding> {
+        let year = self.year.unwrap_or_else(|| reference_year(repo_root));
+        let mut versions = Versions::new(repo_root);
+        files
+            .iter()
+            .filter(|f| self.accepts(f) && !self.allowed(f, repo_root))
+            .filter_map(|f| {
+                let content = std::fs::read_to_string(f).ok()?;
+                Some(self.scan(f, &content, year, &mut versions))
+            })
+            .flatten()
+            .collect()
+    }
+
+    fn analyze_source(&self, path: &Path, content: &str, repo_root: &Path) -> Vec<Finding> {
+        if !self.accepts(path) || self.allowed(path, repo_root) {
+            return Vec::new();
+        }
+        let year = self.year.unwrap_or_else(|| reference_year(repo_root));
+        self.scan(path, content, year, &mut Versions::new(repo_root))
+    }
+}
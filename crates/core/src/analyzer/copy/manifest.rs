@@ -0,0 +1,112 @@
+//! The version a file ships under, from the nearest manifest
+//!
+//! The nearest directory at or above the file with a versioned
+//! `package.json` (`version`), `Cargo.toml` (`[package] version`, or
+//! `[workspace.package] version` for members that inherit it) or
+//! `pyproject.toml` (`[project] version` or `[tool.poetry] version`) decides.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A declared version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    /// As written in the manifest (`5.1.0`)
+    pub text: String,
+    pub major: u64,
+}
+
+impl Version {
+    /// `5.1.0`, `v5`, `5.0.0-beta.1`; `None` without a leading number
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let digits: String = text
+            .trim_start_matches(['v', 'V'])
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect();
+        Some(Self {
+            text: text.to_string(),
+            major: digits.parse().ok()?,
+        })
+    }
+}
+
+/// Looks up versions, reading each directory's manifests once
+pub struct Versions<'a> {
+    root: &'a Path,
+    dirs: HashMap<PathBuf, Option<Version>>,
+}
+
+impl<'a> Versions<'a> {
+    pub fn new(root: &'a Path) -> Self {
+        Self {
+            root,
+            dirs: HashMap::new(),
+        }
+    }
+
+    /// The version of the nearest versioned manifest at or above `file`
+    pub fn of(&mut self, file: &Path) -> Option<Version> {
+        let file = if file.is_absolute() {
+            file.to_path_buf()
+        } else {
+            self.root.join(file)
+        };
+        let mut dir = file.parent();
+        while let Some(current) = dir {
+            let version = self
+                .dirs
+                .entry(current.to_path_buf())
+                .or_insert_with(|| declared(current));
+            if version.is_some() {
+                return version.clone();
+            }
+            if current == self.root || !current.starts_with(self.root) {
+                break;
+            }
+            dir = current.parent();
+        }
+        None
+    }
+}
+
+/// The version declared by a manifest in `dir`
+fn declared(dir: &Path) -> Option<Version> {
+    let read = |name: &str| std::fs::read_to_string(dir.join(name)).ok();
+
+    if let Some(json) =
+        read("package.json").and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    {
+        if let Some(version) = json.get("version").and_then(|v| v.as_str()) {
+            return Version::parse(version);
+        }
+    }
+
+    let toml = |name: &str| read(name).and_then(|c| c.parse::<toml::Table>().ok());
+    let string_at = |doc: &toml::Table, path: &[&str]| -> Option<String> {
+        let (last, tables) = path.split_last()?;
+        let mut table = doc;
+        for key in tables {
+            table = table.get(*key)?.as_table()?;
+        }
+        table.get(*last)?.as_str().map(str::to_string)
+    };
+    if let Some(doc) = toml("Cargo.toml") {
+        // `version.workspace = true` is not a string; the workspace root
+        // further up declares it
+        let version = string_at(&doc, &["package", "version"])
+            .or_else(|| string_at(&doc, &["workspace", "package", "version"]));
+        if let Some(version) = version {
+            return Version::parse(&version);
+        }
+    }
+    if let Some(doc) = toml("pyproject.toml") {
+        let version = string_at(&doc, &["project", "version"])
+            .or_else(|| string_at(&doc, &["tool", "poetry", "version"]));
+        if let Some(version) = version {
+            return Version::parse(&version);
+        }
+    }
+    None
+}
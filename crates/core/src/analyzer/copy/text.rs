@@ -0,0 +1,115 @@
+//! The user-facing text of a file
+//!
+//! Python, JavaScript/TypeScript, Ruby, PHP, Rust and Go sources are parsed
+//! with tree-sitter and only their string literals (and JSX text) are text;
+//! code interpolated into a literal (`f"{brand}"`, `` `${name}` ``,
+//! `"#{x}"`) is blanked out. Markdown and HTML files are text throughout.
+
+use crate::parser::grammar::{language_for, Family, Grammar};
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Document extensions read whole, with the leading dot
+pub const DOT_EXTENSIONS: &[&str] = &[".md", ".mdx", ".markdown", ".html", ".htm"];
+
+/// Nodes holding a literal's text, across the supported grammars
+const LITERALS: &[&str] = &[
+    "string",
+    "template_string",
+    "jsx_text",
+    "encapsed_string",
+    "heredoc_body",
+    "string_literal",
+    "raw_string_literal",
+    "interpreted_string_literal",
+];
+
+/// A stretch of user-facing text at byte offset `start` of the file.
+/// `text` is as long as the source it covers, with code replaced by spaces,
+/// so offsets into it are offsets into the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub text: String,
+}
+
+/// The grammar of `path`, if its string literals are scanned
+pub fn grammar(path: &Path) -> Option<Grammar> {
+    language_for(path).filter(|g| {
+        matches!(
+            g.family,
+            Family::Python | Family::Js | Family::Ruby | Family::Php | Family::Rust | Family::Go
+        )
+    })
+}
+
+/// Whether `path` is a document read as text throughout
+pub fn is_document(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| {
+            DOT_EXTENSIONS
+                .iter()
+                .any(|d| d[1..].eq_ignore_ascii_case(ext))
+        })
+}
+
+/// Whether `path` is scanned at all
+pub fn accepts(path: &Path) -> bool {
+    is_document(path) || grammar(path).is_some()
+}
+
+/// The user-facing text of `content`, the contents of `path`
+pub fn spans(path: &Path, content: &str) -> Vec<Span> {
+    if is_document(path) {
+        return vec![Span {
+            start: 0,
+            text: content.to_string(),
+        }];
+    }
+    let Some(tree) = grammar(path).and_then(|g| g.parse(content)) else {
+        return Vec::new();
+    };
+
+    let mut spans = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if LITERALS.contains(&node.kind()) {
+            spans.push(literal(node, content));
+            continue;
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    spans.sort_by_key(|s| s.start);
+    spans
+}
+
+/// Children of a literal that are part of its text rather than code
+fn is_text(kind: &str) -> bool {
+    kind.contains("content")
+        || kind.contains("fragment")
+        || kind.contains("escape")
+        || matches!(kind, "string_start" | "string_end")
+}
+
+fn literal(node: Node, content: &str) -> Span {
+    let start = node.start_byte();
+    let mut bytes = content.as_bytes()[start..node.end_byte()].to_vec();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if is_text(child.kind()) {
+            continue;
+        }
+        for byte in &mut bytes[child.start_byte() - start..child.end_byte() - start] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    }
+    Span {
+        start,
+        // Whole characters are blanked, so the bytes stay UTF-8
+        text: String::from_utf8(bytes).unwrap_or_default(),
+    }
+}
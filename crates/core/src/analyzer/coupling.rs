@@ -0,0 +1,392 @@
+//! Coupling analyzer (`COUPLE`) — feature envy and inappropriate intimacy
+//!
+//! Both smells are read off the `Calls` and `References` edges between
+//! symbols of the code graph:
+//!
+//! - **envy**: a function whose references into one other file outnumber
+//!   those into its own file by `envy_ratio` (and are at least
+//!   `envy_min_refs`) probably lives in the wrong place
+//! - **intimacy**: two files that reference each other in both directions,
+//!   with `density = edges both ways / symbols of both files` at or above
+//!   `intimacy_density` (and at least `intimacy_min_edges` edges). Only
+//!   pairs sharing an edge are scored, so the cost follows the edge count,
+//!   not the number of file pairs; the `max_pairs` densest are reported.
+//!
+//! Test, generated (`[files] generated_markers`) and `[[vendored]]` files
+//! are left out on both ends, so scaffolding neither gets flagged nor makes
+//! production code look envious. Findings are advisory (`info` unless
+//! `[analyzers.coupling.severity]` says otherwise) and list the symbols most
+//! involved as locations.
+
+use super::{catalog_finding, GraphAnalyzer};
+use crate::code_kind::{CodeKind, CodeKinds};
+use crate::config::{CouplingConfig, RevetConfig};
+use crate::finding::{Finding, FindingDetails, Severity};
+use crate::generated::GeneratedFiles;
+use crate::graph::{CodeGraph, EdgeKind, EdgeMetadata, NodeData, NodeId, NodeKind};
+use crate::msg;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// `[modules]` name of the analyzer
+pub const MODULE: &str = "coupling";
+
+/// Sub-rules, as used in `[analyzers.coupling]` `only`/`disable` and
+/// `severity`
+pub const RULES: &[&str] = &["envy", "intimacy"];
+
+/// Analyzer that reports feature envy and inappropriate intimacy
+pub struct CouplingAnalyzer;
+
+impl Default for CouplingAnalyzer {
+    fn default() -> Self {
+        Self
+    }
+}
+
+impl CouplingAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// A function that references another file more than its own
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envy {
+    pub function: NodeId,
+    /// References into the function's own file
+    pub home: usize,
+    /// The file referenced most, and how often
+    pub file: PathBuf,
+    pub foreign: usize,
+    /// Symbols of `file` referenced, most references first
+    pub symbols: Vec<(NodeId, usize)>,
+}
+
+/// Two files referencing each other densely
+#[derive(Debug, Clone, PartialEq)]
+pub struct Intimacy {
+    /// The pair, in path order
+    pub files: (PathBuf, PathBuf),
+    /// Edges from the first file to the second, and back
+    pub forward: usize,
+    pub backward: usize,
+    /// Symbols of both files together
+    pub symbols: usize,
+    pub density: f64,
+    /// Symbols on either side with the most edges into the other file
+    pub involved: Vec<(NodeId, usize)>,
+}
+
+/// Edges between two files, in path order: first → second, second →
+/// first, and the edges each symbol of the pair takes part in
+#[derive(Default)]
+struct PairEdges {
+    forward: usize,
+    backward: usize,
+    involved: BTreeMap<NodeId, usize>,
+}
+
+/// Which files take part, as decided by their classification
+pub(crate) struct Scope<'a> {
+    graph: &'a CodeGraph,
+    code_kinds: CodeKinds,
+    generated: GeneratedFiles,
+    vendored: Vec<PathBuf>,
+    /// Built-in classification of each file with a `File` node
+    kinds: HashMap<&'a Path, CodeKind>,
+    decided: HashMap<&'a Path, bool>,
+}
+
+impl<'a> Scope<'a> {
+    pub(crate) fn new(graph: &'a CodeGraph, config: &RevetConfig) -> Self {
+        let kinds = graph
+            .nodes()
+            .filter_map(|(_, n)| match n.data() {
+                NodeData::File { code_kind, .. } => Some((n.file_path().as_path(), *code_kind)),
+                _ => None,
+            })
+            .collect();
+        Self {
+            graph,
+            code_kinds: CodeKinds::from_config(&config.files),
+            generated: GeneratedFiles::from_config(&config.files),
+            vendored: config
+                .vendored
+                .iter()
+                .map(|v| PathBuf::from(v.path.trim_start_matches("./")))
+                .collect(),
+            kinds,
+            decided: HashMap::new(),
+        }
+    }
+
+    /// Whether `file` is production or script code, neither generated nor
+    /// vendored
+    pub(crate) fn includes(&mut self, file: &'a Path) -> bool {
+        if let Some(&included) = self.decided.get(file) {
+            return included;
+        }
+        let root = self.graph.root_path();
+        let rel = file.strip_prefix(root).unwrap_or(file);
+        // Path conventions and `[files] test_paths`; the `File` node
+        // carries what the content said
+        let test = self.code_kinds.classify(file, root, "") == CodeKind::Test
+            || self.kinds.get(file) == Some(&CodeKind::Test);
+        let included = !test
+            && !self.vendored.iter().any(|v| rel.starts_with(v))
+            && !self.generated.is_generated(&root.join(rel));
+        self.decided.insert(file, included);
+        included
+    }
+}
+
+/// Whether `kind` is a symbol that references can count against
+fn is_symbol(kind: &NodeKind) -> bool {
+    !matches!(kind, NodeKind::File | NodeKind::Import | NodeKind::Module)
+}
+
+/// Calls and references from `from` to other symbols, with their targets'
+/// files. Calls into external packages point at an import and are skipped.
+fn references<'a>(
+    graph: &'a CodeGraph,
+    from: NodeId,
+) -> impl Iterator<Item = (NodeId, &'a Path)> + 'a {
+    graph.edges_from(from).filter_map(move |(to, edge)| {
+        if !matches!(edge.kind(), EdgeKind::Calls | EdgeKind::References)
+            || matches!(edge.metadata(), Some(EdgeMetadata::ExternalCall { .. }))
+            || to == from
+        {
+            return None;
+        }
+        let target = graph.node(to)?;
+        is_symbol(target.kind()).then_some((to, target.file_path().as_path()))
+    })
+}
+
+/// Every envious function of `graph`, in graph order
+pub fn find_envy(graph: &CodeGraph, config: &RevetConfig) -> Vec<Envy> {
+    let settings = &config.analyzers.coupling;
+    let mut scope = Scope::new(graph, config);
+    let mut found = Vec::new();
+    for (id, node) in graph.nodes() {
+        if !matches!(node.kind(), NodeKind::Function) || !scope.includes(node.file_path()) {
+            continue;
+        }
+        let home_file = node.file_path().as_path();
+        let mut home = 0;
+        let mut by_file: BTreeMap<&Path, BTreeMap<NodeId, usize>> = BTreeMap::new();
+        for (target, file) in references(graph, id) {
+            if file == home_file {
+                home += 1;
+            } else if scope.includes(file) {
+                *by_file.entry(file).or_default().entry(target).or_default() += 1;
+            }
+        }
+        // Most references first; the earliest path wins a tie
+        let Some((file, symbols)) = by_file
+            .into_iter()
+            .max_by_key(|(file, symbols)| (symbols.values().sum::<usize>(), Reverse(*file)))
+        else {
+            continue;
+        };
+        let foreign: usize = symbols.values().sum();
+        if foreign < settings.envy_min_refs || (foreign as f64) < settings.envy_ratio * home as f64
+        {
+            continue;
+        }
+        found.push(Envy {
+            function: id,
+            home,
+            file: file.to_path_buf(),
+            foreign,
+            symbols: ranked(symbols),
+        });
+    }
+    found
+}
+
+/// The intimate file pairs of `graph`, densest first
+pub fn find_intimacy(graph: &CodeGraph, config: &RevetConfig) -> Vec<Intimacy> {
+    let settings = &config.analyzers.coupling;
+    let mut scope = Scope::new(graph, config);
+
+    let mut symbols: HashMap<&Path, usize> = HashMap::new();
+    for (_, node) in graph.nodes() {
+        if is_symbol(node.kind()) {
+            *symbols.entry(node.file_path().as_path()).or_default() += 1;
+        }
+    }
+
+    let mut pairs: BTreeMap<(&Path, &Path), PairEdges> = BTreeMap::new();
+    for (id, node) in graph.nodes() {
+        let from_file = node.file_path().as_path();
+        if !is_symbol(node.kind()) || !scope.includes(from_file) {
+            continue;
+        }
+        for (target, to_file) in references(graph, id) {
+            if to_file == from_file || !scope.includes(to_file) {
+                continue;
+            }
+            let forward = from_file < to_file;
+            let key = if forward {
+                (from_file, to_file)
+            } else {
+                (to_file, from_file)
+            };
+            let pair = pairs.entry(key).or_default();
+            if forward {
+                pair.forward += 1;
+            } else {
+                pair.backward += 1;
+            }
+            *pair.involved.entry(id).or_default() += 1;
+            *pair.involved.entry(target).or_default() += 1;
+        }
+    }
+
+    let mut found: Vec<Intimacy> = pairs
+        .into_iter()
+        .filter(|(_, edges)| {
+            edges.forward > 0
+                && edges.backward > 0
+                && edges.forward + edges.backward >= settings.intimacy_min_edges
+        })
+        .filter_map(|((a, b), edges)| {
+            let count = symbols.get(a).copied().unwrap_or(0) + symbols.get(b).copied().unwrap_or(0);
+            let density = (edges.forward + edges.backward) as f64 / count.max(1) as f64;
+            (density >= settings.intimacy_density).then(|| Intimacy {
+                files: (a.to_path_buf(), b.to_path_buf()),
+                forward: edges.forward,
+                backward: edges.backward,
+                symbols: count,
+                density,
+                involved: ranked(edges.involved),
+            })
+        })
+        .collect();
+    found.sort_by(|x, y| {
+        y.density
+            .total_cmp(&x.density)
+            .then_with(|| x.files.cmp(&y.files))
+    });
+    found.truncate(settings.max_pairs);
+    found
+}
+
+/// Symbols by reference count, most first, then in graph order
+fn ranked(counts: BTreeMap<NodeId, usize>) -> Vec<(NodeId, usize)> {
+    let mut ranked: Vec<(NodeId, usize)> = counts.into_iter().collect();
+    ranked.sort_by_key(|&(id, n)| (Reverse(n), id));
+    ranked
+}
+
+/// `path:line` of the first `limit` symbols, relative to the graph root
+fn locations(graph: &CodeGraph, symbols: &[(NodeId, usize)], limit: usize) -> Vec<String> {
+    symbols
+        .iter()
+        .take(limit)
+        .filter_map(|&(id, _)| graph.node(id))
+        .map(|n| format!("{}:{}", relative(graph, n.file_path()), n.line()))
+        .collect()
+}
+
+fn relative(graph: &CodeGraph, path: &Path) -> String {
+    path.strip_prefix(graph.root_path())
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn severity(config: &CouplingConfig, rule: &str) -> Severity {
+    match config.severity.get(rule).map(String::as_str) {
+        Some("error") => Severity::Error,
+        Some("warning") => Severity::Warning,
+        _ => Severity::Info,
+    }
+}
+
+impl GraphAnalyzer for CouplingAnalyzer {
+    fn name(&self) -> &str {
+        "Coupling"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "COUPLE"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.coupling
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some(MODULE)
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn analyze_graph(&self, graph: &CodeGraph, config: &RevetConfig) -> Vec<Finding> {
+        let settings = &config.analyzers.coupling;
+        let mut findings = Vec::new();
+
+        for envy in find_envy(graph, config) {
+            let Some(node) = graph.node(envy.function) else {
+                continue;
+            };
+            let file = relative(graph, &envy.file);
+            findings.push(Finding {
+                rule: Some("envy".to_string()),
+                details: Some(FindingDetails {
+                    location_list: locations(graph, &envy.symbols, settings.related),
+                    ..Default::default()
+                }),
+                ..catalog_finding(
+                    severity(settings, "envy"),
+                    msg!(
+                        "coupling-envy",
+                        name = node.name(),
+                        file = &file,
+                        foreign = envy.foreign,
+                        home = envy.home
+                    ),
+                    node.file_path().clone(),
+                    node.line(),
+                    Some(msg!("coupling-envy.suggestion", file = &file)),
+                    None,
+                )
+            });
+        }
+
+        for pair in find_intimacy(graph, config) {
+            let (first, second) = &pair.files;
+            findings.push(Finding {
+                rule: Some("intimacy".to_string()),
+                details: Some(FindingDetails {
+                    location_list: locations(graph, &pair.involved, settings.related),
+                    ..Default::default()
+                }),
+                ..catalog_finding(
+                    severity(settings, "intimacy"),
+                    msg!(
+                        "coupling-intimacy",
+                        file = relative(graph, first),
+                        other = relative(graph, second),
+                        forward = pair.forward,
+                        backward = pair.backward,
+                        symbols = pair.symbols,
+                        density = format!("{:.2}", pair.density)
+                    ),
+                    first.clone(),
+                    1,
+                    Some(msg!("coupling-intimacy.suggestion")),
+                    None,
+                )
+            });
+        }
+
+        findings
+    }
+}
@@ -2,8 +2,10 @@
 //!
 //! Allows teams to define project-specific patterns (banned APIs, coding conventions,
 //! sensitive keywords) directly in config, without writing Rust code.
-//! All findings use the `CUSTOM-` prefix.
+//! Findings use the `CUSTOM-` prefix, or the rule's own `prefix`; one
+//! analyzer runs per prefix.
 
+use crate::analyzer::registry::CUSTOM;
 use crate::analyzer::{make_finding, Analyzer};
 use crate::config::RevetConfig;
 use crate::finding::{Finding, FixKind, Severity};
@@ -24,19 +26,30 @@ struct CompiledRule {
 
 /// Analyzer that runs user-defined regex rules from `.revet.toml`
 pub struct CustomRulesAnalyzer {
+    prefix: String,
     rules: Vec<CompiledRule>,
     /// Leaked static references for the `extra_extensions()` trait method
     leaked_exts: Vec<&'static str>,
 }
 
 impl CustomRulesAnalyzer {
-    /// Build from config, compiling regexes and globs. Invalid patterns are
-    /// skipped with a warning on stderr.
+    /// Build from the config's rules without a `prefix`, compiling regexes
+    /// and globs. Invalid patterns are skipped with a warning on stderr.
     pub fn from_config(config: &RevetConfig) -> Self {
+        Self::for_prefix(config, CUSTOM)
+    }
+
+    /// Like [`from_config`](Self::from_config), for the rules whose findings
+    /// use `prefix`
+    pub fn for_prefix(config: &RevetConfig, prefix: &str) -> Self {
         let mut rules = Vec::new();
         let mut ext_set = std::collections::HashSet::new();
 
-        for rule in &config.rules {
+        for rule in config
+            .rules
+            .iter()
+            .filter(|r| r.prefix.as_deref().unwrap_or(CUSTOM) == prefix)
+        {
             // Compile regex
             let regex = match Regex::new(&rule.pattern) {
                 Ok(r) => r,
@@ -129,11 +142,54 @@ impl CustomRulesAnalyzer {
             .map(|s| &*Box::leak(s.clone().into_boxed_str()))
             .collect();
 
-        Self { rules, leaked_exts }
+        Self {
+            prefix: prefix.to_string(),
+            rules,
+            leaked_exts,
+        }
     }
 
     /// Check if a file matches any of a rule's glob patterns.
     /// If the rule has no globs, it matches all files.
+    fn scan_source(&self, file: &Path, content: &str) -> Vec<Finding> {
+        let Some(file_name) = file.file_name().and_then(|n| n.to_str()) else {
+            return Vec::new();
+        };
+
+        let mut findings = Vec::new();
+        for (line_num, line) in content.lines().enumerate() {
+            // First matching rule wins per line
+            for rule in &self.rules {
+                if !Self::file_matches_rule(file_name, rule) {
+                    continue;
+                }
+
+                if !rule.regex.is_match(line) {
+                    continue;
+                }
+
+                // Negative filter
+                if let Some(ref reject) = rule.reject_if_contains {
+                    if line.contains(reject.as_str()) {
+                        continue;
+                    }
+                }
+
+                findings.push(make_finding(
+                    rule.severity,
+                    rule.message.clone(),
+                    file.to_path_buf(),
+                    line_num + 1,
+                    rule.suggestion.clone(),
+                    rule.fix_kind.clone(),
+                ));
+                break; // One finding per line
+            }
+        }
+
+        findings
+    }
+
     fn file_matches_rule(file_name: &str, rule: &CompiledRule) -> bool {
         if rule.globs.is_empty() {
             return true;
@@ -148,61 +204,41 @@ impl Analyzer for CustomRulesAnalyzer {
     }
 
     fn finding_prefix(&self) -> &str {
-        "CUSTOM"
+        &self.prefix
     }
 
     fn is_enabled(&self, _config: &RevetConfig) -> bool {
         !self.rules.is_empty()
     }
 
+    fn accepts(&self, path: &Path) -> bool {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|name| self.rules.iter().any(|r| Self::file_matches_rule(name, r)))
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
     fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
         let mut findings = Vec::new();
 
         for file in files {
-            let file_name = match file.file_name().and_then(|n| n.to_str()) {
-                Some(n) => n,
-                None => continue,
-            };
-
             let content = match std::fs::read_to_string(file) {
                 Ok(c) => c,
                 Err(_) => continue,
             };
-
-            for (line_num, line) in content.lines().enumerate() {
-                // First matching rule wins per line
-                for rule in &self.rules {
-                    if !Self::file_matches_rule(file_name, rule) {
-                        continue;
-                    }
-
-                    if !rule.regex.is_match(line) {
-                        continue;
-                    }
-
-                    // Negative filter
-                    if let Some(ref reject) = rule.reject_if_contains {
-                        if line.contains(reject.as_str()) {
-                            continue;
-                        }
-                    }
-
-                    findings.push(make_finding(
-                        rule.severity,
-                        rule.message.clone(),
-                        file.to_path_buf(),
-                        line_num + 1,
-                        rule.suggestion.clone(),
-                        rule.fix_kind.clone(),
-                    ));
-                    break; // One finding per line
-                }
-            }
+            findings.extend(self.scan_source(file, &content));
         }
 
         findings
     }
 
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        self.scan_source(path, content)
+    }
+
     fn extra_extensions(&self) -> &[&str] {
         &self.leaked_exts
     }
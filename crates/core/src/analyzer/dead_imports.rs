@@ -14,13 +14,17 @@
 //! - Implicit usages (e.g. React in JSX with old transform) may produce false positives.
 //! - Multi-line import statements use the first line for alias detection.
 
-use crate::analyzer::GraphAnalyzer;
+use crate::analyzer::{catalog_finding, file_timing, GraphAnalyzer};
 use crate::config::RevetConfig;
-use crate::finding::{Finding, Severity};
+use crate::finding::{Finding, InsertContext, Severity, SuggestedCode};
 use crate::graph::{CodeGraph, NodeData, NodeKind};
+use crate::msg;
+use crate::suggestion;
+use regex::Regex;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 // ── Public struct ─────────────────────────────────────────────────────────────
 
@@ -103,6 +107,134 @@ fn is_ident_byte(b: u8) -> bool {
     b.is_ascii_alphanumeric() || b == b'_'
 }
 
+/// The single-line import `line` without `name`, when it imports other
+/// names too. Handles `from m import a, b`, `import a, b` and
+/// `import d, { a, b } from 'm'`.
+fn without_name(path: &Path, line: &str, name: &str) -> Option<SuggestedCode> {
+    static PYTHON: OnceLock<Regex> = OnceLock::new();
+    static JS: OnceLock<Regex> = OnceLock::new();
+    let python = PYTHON.get_or_init(|| {
+        Regex::new(r"^\s*((?:from\s+\S+\s+)?import\s+)(\(?)([^()#{}]+?)(\)?)\s*$").unwrap()
+    });
+    let js = JS.get_or_init(|| {
+        Regex::new(r#"^\s*(import\s+(?:type\s+)?)(?:([\w$]+)\s*,\s*)?\{([^{}]*)\}(\s*from\s*["'][^"']+["'];?)\s*$"#)
+            .unwrap()
+    });
+    // Entries may be aliased (`a as b`)
+    let keep = |list: &str| -> Vec<String> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty() && entry.split_whitespace().next() != Some(name))
+            .map(String::from)
+            .collect()
+    };
+
+    let snippet = if let Some(caps) = js.captures(line) {
+        let named = keep(&caps[3]);
+        match caps.get(2).map(|d| d.as_str()) {
+            Some(default) if default == name && !named.is_empty() => {
+                format!("{}{{ {} }}{}", &caps[1], named.join(", "), &caps[4])
+            }
+            Some(default) if named.is_empty() => format!("{}{}{}", &caps[1], default, &caps[4]),
+            Some(default) => {
+                format!(
+                    "{}{}, {{ {} }}{}",
+                    &caps[1],
+                    default,
+                    named.join(", "),
+                    &caps[4]
+                )
+            }
+            None if named.is_empty() => return None,
+            None => format!("{}{{ {} }}{}", &caps[1], named.join(", "), &caps[4]),
+        }
+    } else if let Some(caps) = python.captures(line) {
+        let names = keep(&caps[3]);
+        if names.is_empty() || caps[2].len() != caps[4].len() {
+            return None;
+        }
+        format!("{}{}{}{}", &caps[1], &caps[2], names.join(", "), &caps[4])
+    } else {
+        return None;
+    };
+    if snippet.trim() == line.trim() {
+        return None;
+    }
+    suggestion::code(path, snippet, InsertContext::Line)
+}
+
+/// Collect all Import nodes grouped by file path.
+/// Each entry: (line_number, imported_names)
+fn imports_by_file(graph: &CodeGraph) -> HashMap<PathBuf, Vec<(usize, Vec<String>)>> {
+    let mut by_file: HashMap<PathBuf, Vec<(usize, Vec<String>)>> = HashMap::new();
+
+    for (_, node) in graph.nodes() {
+        if !matches!(node.kind(), NodeKind::Import) {
+            continue;
+        }
+        let NodeData::Import { imported_names, .. } = node.data() else {
+            continue;
+        };
+        by_file
+            .entry(node.file_path().clone())
+            .or_default()
+            .push((node.line(), imported_names.clone()));
+    }
+    by_file
+}
+
+/// Flag imported names in one file that are never referenced in `content`.
+fn check_file(
+    file_path: &Path,
+    imports: &[(usize, Vec<String>)],
+    content: &str,
+    findings: &mut Vec<Finding>,
+) {
+    let lines: Vec<&str> = content.lines().collect();
+
+    for (import_line_no, imported_names) in imports {
+        // Retrieve the raw import line for alias detection (1-indexed).
+        let import_line_str = if *import_line_no > 0 && *import_line_no <= lines.len() {
+            lines[import_line_no - 1]
+        } else {
+            ""
+        };
+
+        for name in imported_names {
+            // Skip wildcard imports — we can't know what they bring in.
+            if name == "*" || name.is_empty() {
+                continue;
+            }
+
+            // Skip language meta-keywords that are never real usages.
+            if ALWAYS_SKIP.contains(&name.as_str()) {
+                continue;
+            }
+
+            // Determine the local binding: prefer an explicit alias.
+            let local_name = extract_alias(import_line_str, name).unwrap_or_else(|| name.clone());
+
+            // Count word-boundary occurrences across the whole file.
+            // A count of ≤ 1 means the name only appears on the import
+            // line itself and is never referenced in the file body.
+            let occurrences = count_word(content, &local_name);
+
+            if occurrences <= 1 {
+                let mut finding = catalog_finding(
+                    Severity::Warning,
+                    msg!("dead-imports-unused", name = &local_name),
+                    file_path.to_path_buf(),
+                    *import_line_no,
+                    Some(msg!("dead-imports-unused.suggestion", name = &local_name)),
+                    None,
+                );
+                finding.suggestion_code = without_name(file_path, import_line_str, name);
+                findings.push(finding);
+            }
+        }
+    }
+}
+
 // ── GraphAnalyzer impl ────────────────────────────────────────────────────────
 
 impl GraphAnalyzer for DeadImportsAnalyzer {
@@ -118,81 +250,39 @@ impl GraphAnalyzer for DeadImportsAnalyzer {
         config.modules.dead_imports
     }
 
-    fn analyze_graph(&self, graph: &CodeGraph, _config: &RevetConfig) -> Vec<Finding> {
-        // Collect all Import nodes grouped by file path.
-        // Each entry: (line_number, imported_names)
-        let mut by_file: HashMap<PathBuf, Vec<(usize, Vec<String>)>> = HashMap::new();
-
-        for (_, node) in graph.nodes() {
-            if !matches!(node.kind(), NodeKind::Import) {
-                continue;
-            }
-            let NodeData::Import { imported_names, .. } = node.data() else {
-                continue;
-            };
-            by_file
-                .entry(node.file_path().clone())
-                .or_default()
-                .push((node.line(), imported_names.clone()));
-        }
+    fn module(&self) -> Option<&'static str> {
+        Some("dead_imports")
+    }
 
+    fn analyze_graph(&self, graph: &CodeGraph, _config: &RevetConfig) -> Vec<Finding> {
         let mut findings = Vec::new();
 
-        for (file_path, imports) in &by_file {
+        for (file_path, imports) in &imports_by_file(graph) {
             let content = match fs::read_to_string(file_path) {
                 Ok(c) => c,
                 Err(_) => continue,
             };
-
-            let lines: Vec<&str> = content.lines().collect();
-
-            for (import_line_no, imported_names) in imports {
-                // Retrieve the raw import line for alias detection (1-indexed).
-                let import_line_str = if *import_line_no > 0 && *import_line_no <= lines.len() {
-                    lines[import_line_no - 1]
-                } else {
-                    ""
-                };
-
-                for name in imported_names {
-                    // Skip wildcard imports — we can't know what they bring in.
-                    if name == "*" || name.is_empty() {
-                        continue;
-                    }
-
-                    // Skip language meta-keywords that are never real usages.
-                    if ALWAYS_SKIP.contains(&name.as_str()) {
-                        continue;
-                    }
-
-                    // Determine the local binding: prefer an explicit alias.
-                    let local_name =
-                        extract_alias(import_line_str, name).unwrap_or_else(|| name.clone());
-
-                    // Count word-boundary occurrences across the whole file.
-                    // A count of ≤ 1 means the name only appears on the import
-                    // line itself and is never referenced in the file body.
-                    let occurrences = count_word(&content, &local_name);
-
-                    if occurrences <= 1 {
-                        findings.push(Finding {
-                            id: String::new(),
-                            severity: Severity::Warning,
-                            message: format!("`{local_name}` is imported but never used"),
-                            file: file_path.clone(),
-                            line: *import_line_no,
-                            affected_dependents: 0,
-                            suggestion: Some(format!("Remove the unused import of `{local_name}`")),
-                            fix_kind: None,
-                            ..Default::default()
-                        });
-                    }
-                }
-            }
+            file_timing::time_file(file_path, || {
+                check_file(file_path, imports, &content, &mut findings)
+            });
         }
 
         findings
     }
+
+    fn analyze_file(
+        &self,
+        graph: &CodeGraph,
+        file: &Path,
+        content: &str,
+        _config: &RevetConfig,
+    ) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        if let Some(imports) = imports_by_file(graph).get(file) {
+            check_file(file, imports, content, &mut findings);
+        }
+        findings
+    }
 }
 
 // ── Unit tests ────────────────────────────────────────────────────────────────
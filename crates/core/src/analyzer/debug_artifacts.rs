@@ -0,0 +1,188 @@
+//! Debug-artifacts analyzer — debugger statements, print debugging and
+//! commented-out code left in committed source
+//!
+//! Python, JavaScript/TypeScript, Ruby, PHP, Rust and Go files are parsed
+//! with tree-sitter (see [`calls`] and [`comments`]):
+//!
+//! - `debugger`: `debugger;`, `breakpoint()`, `pdb.set_trace()`,
+//!   `binding.pry`, `byebug`, `dd()`/`dump()`, `dbg!()`,
+//!   `runtime.Breakpoint()`. A statement alone on its line whose arguments
+//!   call nothing gets a fix that deletes it
+//! - `debug_print`: a print whose message is a placeholder word (`"here"`,
+//!   `"asdf"`, `[analyzers.debug_artifacts] print_patterns`) or that prints
+//!   a single variable with no message. Medium confidence
+//! - `commented_code`: `comment_lines` or more consecutive comment lines that
+//!   parse as code of the file's language
+//!
+//! Each sub-rule's severity comes from `[analyzers.debug_artifacts.severity]`
+//! (defaults: error, warning, info).
+//!
+//! Finding prefix: `DBG-`
+
+pub mod calls;
+pub mod comments;
+
+use crate::analyzer::{catalog_finding, Analyzer};
+use crate::config::{DebugArtifactsConfig, RevetConfig};
+use crate::finding::{Confidence, Finding, FixKind, Severity};
+use crate::msg;
+use calls::{Artifact, Kind, PrintPatterns};
+use std::path::{Path, PathBuf};
+
+/// Module name of the debug-artifacts analyzer
+pub const MODULE: &str = "debug_artifacts";
+
+/// Sub-rules of the debug-artifacts analyzer
+pub const RULES: &[&str] = &["debugger", "debug_print", "commented_code"];
+
+/// Analyzer that reports debugging leftovers
+pub struct DebugArtifactsAnalyzer {
+    patterns: PrintPatterns,
+    comment_lines: usize,
+    debugger: Severity,
+    debug_print: Severity,
+    commented_code: Severity,
+}
+
+impl DebugArtifactsAnalyzer {
+    /// Create a debug-artifacts analyzer with the default settings
+    pub fn new() -> Self {
+        Self::with_config(&DebugArtifactsConfig::default())
+    }
+
+    /// Create a debug-artifacts analyzer honouring `[analyzers.debug_artifacts]`
+    pub fn with_config(config: &DebugArtifactsConfig) -> Self {
+        let severity =
+            |rule: &str, default: Severity| match config.severity.get(rule).map(String::as_str) {
+                Some("error") => Severity::Error,
+                Some("warning") => Severity::Warning,
+                Some("info") => Severity::Info,
+                _ => default,
+            };
+        Self {
+            patterns: PrintPatterns::new(&config.print_patterns),
+            comment_lines: config.comment_lines,
+            debugger: severity("debugger", Severity::Error),
+            debug_print: severity("debug_print", Severity::Warning),
+            commented_code: severity("commented_code", Severity::Info),
+        }
+    }
+
+    fn scan_source(&self, path: &Path, content: &str) -> Vec<Finding> {
+        let mut findings: Vec<Finding> = calls::scan(path, content, &self.patterns)
+            .iter()
+            .map(|artifact| self.artifact(path, artifact))
+            .collect();
+        findings.extend(
+            comments::blocks(path, content, self.comment_lines)
+                .into_iter()
+                .map(|block| Finding {
+                    rule: Some("commented_code".to_string()),
+                    confidence: Confidence::Medium,
+                    ..catalog_finding(
+                        self.commented_code,
+                        msg!(
+                            "debug-commented-code",
+                            lines = block.lines(),
+                            start = block.start,
+                            end = block.end
+                        ),
+                        path.to_path_buf(),
+                        block.start,
+                        Some(msg!("debug-commented-code.suggestion")),
+                        None,
+                    )
+                }),
+        );
+        findings.sort_by_key(|f| f.line);
+        findings
+    }
+
+    fn artifact(&self, path: &Path, artifact: &Artifact) -> Finding {
+        let call = artifact.name.as_str();
+        let finding = match artifact.kind {
+            Kind::Debugger => Finding {
+                rule: Some("debugger".to_string()),
+                ..catalog_finding(
+                    self.debugger,
+                    msg!("debug-debugger", call = call),
+                    path.to_path_buf(),
+                    artifact.line,
+                    Some(msg!("debug-debugger.suggestion")),
+                    artifact
+                        .removable
+                        .as_ref()
+                        .map(|statement| FixKind::RemoveStatement {
+                            statement: statement.clone(),
+                        }),
+                )
+            },
+            Kind::Print => Finding {
+                rule: Some("debug_print".to_string()),
+                confidence: Confidence::Medium,
+                ..catalog_finding(
+                    self.debug_print,
+                    msg!("debug-print", call = call),
+                    path.to_path_buf(),
+                    artifact.line,
+                    Some(msg!("debug-print.suggestion")),
+                    None,
+                )
+            },
+        };
+        Finding {
+            column: Some(artifact.column),
+            end_column: artifact.end_column,
+            ..finding
+        }
+    }
+}
+
+impl Default for DebugArtifactsAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer for DebugArtifactsAnalyzer {
+    fn name(&self) -> &str {
+        "Debug artifacts"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "DBG"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.debug_artifacts
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some(MODULE)
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        calls::grammar(path).is_some()
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
+        files
+            .iter()
+            .filter(|f| self.accepts(f))
+            .filter_map(|f| Some(self.scan_source(f, &std::fs::read_to_string(f).ok()?)))
+            .flatten()
+            .collect()
+    }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        self.scan_source(path, content)
+    }
+}
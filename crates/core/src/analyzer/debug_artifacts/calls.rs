@@ -0,0 +1,441 @@
+//! Debugger statements and print debugging in source code
+//!
+//! Python, JavaScript/TypeScript, Ruby, PHP, Rust and Go sources are parsed
+//! with tree-sitter. Debugger statements are recognised by name alone
+//! (`debugger;`, `breakpoint()`, `binding.pry`, `dd($x)`, `dbg!(x)`). A print
+//! call is only leftover debugging when what it prints says nothing:
+//!
+//! - its message, with placeholders and punctuation removed, matches one of
+//!   the print patterns (`"here"`, `"got here {:?}"`, `"asdf"`), or
+//! - it has no message and prints a single variable (`print(user)`,
+//!   `console.log(`${order}`)`, `println!("{:?}", x)`)
+//!
+//! A print with a message of its own (`print("Loaded", n, "rows")`) is output
+//! the program means to produce and is left alone.
+
+use crate::parser::grammar::{language_for, Family, Grammar};
+use crate::trace::{self, Decision};
+use regex::Regex;
+use std::path::Path;
+use std::sync::OnceLock;
+use tree_sitter::Node;
+
+/// Default print patterns (`[analyzers.debug_artifacts] print_patterns`)
+pub const PRINT_PATTERNS: &[&str] = &[
+    "here",
+    "got here",
+    "in here",
+    "made it( here)?",
+    "reached( here)?",
+    "test(ing)?",
+    "asdf\\w*",
+    "qwe\\w*",
+    "foo",
+    "bar",
+    "baz",
+    "xxx+",
+    "yyy+",
+    "zzz+",
+    "wtf",
+    "debug",
+    "\\d+",
+];
+
+/// Format placeholders: `{}`, `{x:?}`, `${x}`, `#{x}`, `%s`, `%-5.2f`, `$x`
+fn placeholder() -> &'static Regex {
+    static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+    PLACEHOLDER.get_or_init(|| {
+        Regex::new(
+            r"\$\{[^}]*\}|#\{[^}]*\}|\{[^{}]*\}|%[-+ #0]*\d*(?:\.\d+)?[a-zA-Z]|\$[A-Za-z_]\w*",
+        )
+        .expect("valid placeholder regex")
+    })
+}
+
+/// Escape sequences, which are not part of a message
+fn escape() -> &'static Regex {
+    static ESCAPE: OnceLock<Regex> = OnceLock::new();
+    ESCAPE.get_or_init(|| Regex::new(r"\\[nrt0]").expect("valid escape regex"))
+}
+
+/// Calls that stop in a debugger or dump and halt
+fn debuggers(family: Family) -> &'static [&'static str] {
+    match family {
+        Family::Python => &[
+            "breakpoint",
+            "pdb.set_trace",
+            "ipdb.set_trace",
+            "pudb.set_trace",
+            "pdb.post_mortem",
+        ],
+        // `debugger;` is a statement of its own
+        Family::Js => &[],
+        Family::Ruby => &[
+            "binding.pry",
+            "binding.irb",
+            "binding.break",
+            "binding.b",
+            "byebug",
+            "debugger",
+            "remote_byebug",
+        ],
+        Family::Php => &["dd", "dump", "ddd", "xdebug_break"],
+        Family::Rust => &["dbg!"],
+        Family::Go => &["runtime.Breakpoint"],
+        _ => &[],
+    }
+}
+
+/// Calls that print to the console
+fn prints(family: Family) -> &'static [&'static str] {
+    match family {
+        Family::Python => &["print", "pprint", "pprint.pprint"],
+        Family::Js => &[
+            "console.log",
+            "console.debug",
+            "console.dir",
+            "console.trace",
+        ],
+        Family::Ruby => &["puts", "p", "pp", "print"],
+        Family::Php => &["var_dump", "print_r", "var_export"],
+        Family::Rust => &["println!", "print!", "eprintln!", "eprint!"],
+        Family::Go => &["fmt.Println", "fmt.Printf", "fmt.Print", "println", "print"],
+        _ => &[],
+    }
+}
+
+/// The grammar of `path`, if it is scanned
+pub fn grammar(path: &Path) -> Option<Grammar> {
+    language_for(path).filter(|g| {
+        matches!(
+            g.family,
+            Family::Python | Family::Js | Family::Ruby | Family::Php | Family::Rust | Family::Go
+        )
+    })
+}
+
+/// Compiled print patterns
+#[derive(Debug, Clone)]
+pub struct PrintPatterns(Option<Regex>);
+
+impl PrintPatterns {
+    /// Match a whole message against any of `patterns`, ignoring case and a
+    /// trailing number. Patterns that do not compile are skipped (config
+    /// validation reports them).
+    pub fn new(patterns: &[String]) -> Self {
+        let valid: Vec<String> = patterns
+            .iter()
+            .filter(|p| Regex::new(p).is_ok())
+            .map(|p| format!("(?:{})", p))
+            .collect();
+        if valid.is_empty() {
+            return Self(None);
+        }
+        let combined = format!(r"(?i)^(?:{})\s*\d*$", valid.join("|"));
+        Self(Regex::new(&combined).ok())
+    }
+
+    fn matches(&self, message: &str) -> bool {
+        self.0.as_ref().is_some_and(|re| re.is_match(message))
+    }
+}
+
+/// What an artifact is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Debugger,
+    Print,
+}
+
+/// One debugger statement or leftover print
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    pub kind: Kind,
+    /// Callee as written, without whitespace: `pdb.set_trace`, `console.log`,
+    /// `dbg!`, or `debugger` for the JavaScript statement
+    pub name: String,
+    pub line: usize,
+    /// 1-based character columns of the call on `line`
+    pub column: usize,
+    pub end_column: Option<usize>,
+    /// Text of the whole statement, when it stands alone on its line and
+    /// deleting it cannot drop other work
+    pub removable: Option<String>,
+}
+
+/// Every artifact in one source, in source order
+pub fn scan(path: &Path, content: &str, patterns: &PrintPatterns) -> Vec<Artifact> {
+    let Some(grammar) = grammar(path) else {
+        return Vec::new();
+    };
+    let Some(tree) = grammar.parse(content) else {
+        return Vec::new();
+    };
+    let mut scanner = Scanner {
+        family: grammar.family,
+        path,
+        source: content,
+        patterns,
+        artifacts: Vec::new(),
+    };
+    scanner.walk(tree.root_node());
+    scanner.artifacts
+}
+
+/// Parents a Ruby statement sits in directly (Ruby has no expression
+/// statement node)
+const RUBY_BODIES: &[&str] = &[
+    "program",
+    "body_statement",
+    "then",
+    "else",
+    "do_block",
+    "block_body",
+    "begin",
+    "ensure",
+];
+
+/// A print argument, as far as the decision needs it
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Arg {
+    /// String literal contents, placeholders included
+    Str(String),
+    /// A variable or a field of one
+    Var,
+    Other,
+}
+
+struct Scanner<'a> {
+    family: Family,
+    path: &'a Path,
+    source: &'a str,
+    patterns: &'a PrintPatterns,
+    artifacts: Vec<Artifact>,
+}
+
+impl<'a> Scanner<'a> {
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.source.as_bytes()).unwrap_or("")
+    }
+
+    fn walk(&mut self, node: Node) {
+        match node.kind() {
+            "debugger_statement" => self.push(Kind::Debugger, "debugger".to_string(), node),
+            "call" | "call_expression" | "function_call_expression" | "macro_invocation" => {
+                self.call(node)
+            }
+            // A bare `byebug` in Ruby is an identifier, not a call
+            "identifier"
+                if self.family == Family::Ruby
+                    && node
+                        .parent()
+                        .is_some_and(|p| RUBY_BODIES.contains(&p.kind())) =>
+            {
+                let name = self.text(node);
+                if debuggers(self.family).contains(&name) {
+                    self.push(Kind::Debugger, name.to_string(), node);
+                }
+            }
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.walk(child);
+        }
+    }
+
+    /// Callee text without whitespace: `pdb.set_trace`, `binding.pry`, `dbg!`
+    fn callee(&self, node: Node) -> Option<String> {
+        let text = match node.kind() {
+            "macro_invocation" => format!("{}!", self.text(node.child_by_field_name("macro")?)),
+            "call" if self.family == Family::Ruby => {
+                let method = self.text(node.child_by_field_name("method")?);
+                match node.child_by_field_name("receiver") {
+                    Some(receiver) => format!("{}.{}", self.text(receiver), method),
+                    None => method.to_string(),
+                }
+            }
+            _ => self.text(node.child_by_field_name("function")?).to_string(),
+        };
+        Some(text.chars().filter(|c| !c.is_whitespace()).collect())
+    }
+
+    fn call(&mut self, node: Node) {
+        let Some(callee) = self.callee(node) else {
+            return;
+        };
+        if debuggers(self.family).contains(&callee.as_str()) {
+            self.push(Kind::Debugger, callee, node);
+        } else if prints(self.family).contains(&callee.as_str()) {
+            let args = self.args(node);
+            if self.is_debug_print(&args) {
+                self.push(Kind::Print, callee, node);
+            } else {
+                let line = node.start_position().row + 1;
+                trace::record(self.path, line, Decision::Skipped, || {
+                    format!(
+                        "`{}` prints a message rather than inspecting a value",
+                        callee
+                    )
+                });
+            }
+        }
+    }
+
+    fn push(&mut self, kind: Kind, name: String, node: Node) {
+        let start = node.start_position();
+        let end = node.end_position();
+        let line = self.source.lines().nth(start.row).unwrap_or("");
+        let column = char_column(line, start.column);
+        let end_column = (end.row == start.row).then(|| char_column(line, end.column));
+        let removable = match kind {
+            Kind::Debugger => self.removable(node),
+            Kind::Print => None,
+        };
+        self.artifacts.push(Artifact {
+            kind,
+            name,
+            line: start.row + 1,
+            column,
+            end_column,
+            removable,
+        });
+    }
+
+    /// The statement `node` makes up, when it is alone on one line and its
+    /// arguments call nothing
+    fn removable(&self, node: Node) -> Option<String> {
+        let parent = node.parent()?;
+        let statement = if parent.kind() == "expression_statement" {
+            parent
+        } else if node.kind() == "debugger_statement" || RUBY_BODIES.contains(&parent.kind()) {
+            node
+        } else {
+            return None;
+        };
+        if statement.start_position().row != statement.end_position().row || has_work(node) {
+            return None;
+        }
+        let text = self.text(statement);
+        let line = self.source.lines().nth(statement.start_position().row)?;
+        let bare = |s: &str| s.trim().trim_end_matches(';').trim_end().to_string();
+        (bare(line) == bare(text)).then(|| text.to_string())
+    }
+
+    /// Arguments of a call, without comments and keyword arguments
+    fn args(&self, node: Node) -> Vec<Arg> {
+        if node.kind() == "macro_invocation" {
+            return self.macro_args(node);
+        }
+        let Some(list) = node.child_by_field_name("arguments") else {
+            return Vec::new();
+        };
+        let mut cursor = list.walk();
+        let args = list
+            .named_children(&mut cursor)
+            .filter(|a| !matches!(a.kind(), "comment" | "keyword_argument" | "pair"))
+            .map(|a| match a.kind() {
+                "argument" => a.named_child(0).map_or(Arg::Other, |v| self.arg(v)),
+                _ => self.arg(a),
+            })
+            .collect();
+        args
+    }
+
+    /// Arguments of a Rust macro: its token tree split at top-level commas
+    fn macro_args(&self, node: Node) -> Vec<Arg> {
+        let mut cursor = node.walk();
+        let Some(tree) = node
+            .named_children(&mut cursor)
+            .find(|c| c.kind() == "token_tree")
+        else {
+            return Vec::new();
+        };
+        let mut groups: Vec<Vec<Node>> = vec![Vec::new()];
+        let mut cursor = tree.walk();
+        for child in tree.children(&mut cursor) {
+            match child.kind() {
+                "(" | ")" | "[" | "]" | "{" | "}" => {}
+                "," => groups.push(Vec::new()),
+                _ => groups.last_mut().expect("one group").push(child),
+            }
+        }
+        groups
+            .into_iter()
+            .filter(|g| !g.is_empty())
+            .map(|g| match g.as_slice() {
+                [one] => self.arg(*one),
+                _ => Arg::Other,
+            })
+            .collect()
+    }
+
+    fn arg(&self, node: Node) -> Arg {
+        match node.kind() {
+            "string"
+            | "template_string"
+            | "encapsed_string"
+            | "string_literal"
+            | "raw_string_literal"
+            | "interpreted_string_literal" => Arg::Str(unquote(self.text(node)).to_string()),
+            "identifier"
+            | "variable_name"
+            | "instance_variable"
+            | "attribute"
+            | "member_expression"
+            | "selector_expression"
+            | "field_expression" => Arg::Var,
+            "parenthesized_expression" => node.named_child(0).map_or(Arg::Other, |n| self.arg(n)),
+            _ => Arg::Other,
+        }
+    }
+
+    fn is_debug_print(&self, args: &[Arg]) -> bool {
+        let (message, rest) = match args.split_first() {
+            Some((Arg::Str(message), rest)) => (message, rest),
+            Some((Arg::Var, [])) => return true,
+            _ => return false,
+        };
+        let message = escape().replace_all(message, " ");
+        let placeholders = placeholder().find_iter(&message).count();
+        let stripped = placeholder().replace_all(&message, " ");
+        let text = stripped.trim_matches(|c: char| c.is_whitespace() || c.is_ascii_punctuation());
+        if !text.is_empty() {
+            return self.patterns.matches(text);
+        }
+        // No message of its own: a lone value is being inspected, either
+        // interpolated or passed after a bare format string
+        placeholders.max(rest.len()) == 1 && rest.iter().all(|a| *a == Arg::Var)
+    }
+}
+
+/// Whether evaluating `node`'s arguments does work of its own (calls,
+/// assignments), which deleting the statement would drop
+fn has_work(node: Node) -> bool {
+    let mut cursor = node.walk();
+    let found = node.named_children(&mut cursor).any(|child| {
+        let kind = child.kind();
+        kind.contains("call")
+            || kind.contains("invocation")
+            || kind.contains("assignment")
+            || kind == "token_tree" && child.parent().is_some_and(|p| p.kind() == "token_tree")
+            || has_work(child)
+    });
+    found
+}
+
+/// 1-based character column of byte offset `byte` in `line`
+fn char_column(line: &str, byte: usize) -> usize {
+    line.get(..byte).map_or(byte, |s| s.chars().count()) + 1
+}
+
+/// Strip string prefixes (`f`, `r`, `b`) and quotes
+fn unquote(text: &str) -> &str {
+    let text = text.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    for quote in ["\"\"\"", "'''", "\"", "'", "`"] {
+        if let Some(inner) = text.strip_prefix(quote).and_then(|t| t.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    text
+}
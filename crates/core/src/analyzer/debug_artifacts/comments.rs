@@ -0,0 +1,144 @@
+//! Commented-out code blocks
+//!
+//! A run of consecutive full-line comments (`#` or `//`, per language) is
+//! commented-out code when, with the comment markers removed and the text
+//! dedented, it parses without errors in the file's own language. Most of the
+//! lines must also contain code punctuation (brackets, `=`, `;`), since a few
+//! single words parse as bare identifiers. When the whole run does not parse,
+//! leading lines are dropped one at a time, so a prose comment introducing
+//! the old code does not hide it.
+//!
+//! Doc comments (`///`, `//!`), shebangs, PHP attributes (`#[...]`) and block
+//! comments are never counted.
+
+use super::calls::grammar;
+use crate::parser::grammar::Family;
+use std::path::Path;
+use tree_sitter::Parser;
+
+/// One block of commented-out code, by 1-based line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Block {
+    pub fn lines(&self) -> usize {
+        self.end - self.start + 1
+    }
+}
+
+/// Every block of at least `min_lines` non-empty lines of commented-out code
+pub fn blocks(path: &Path, content: &str, min_lines: usize) -> Vec<Block> {
+    let Some(grammar) = grammar(path) else {
+        return Vec::new();
+    };
+    let family = grammar.family;
+    let mut parser = Parser::new();
+    if parser.set_language(&grammar.language).is_err() {
+        return Vec::new();
+    }
+
+    let mut blocks = Vec::new();
+    let mut run: Vec<&str> = Vec::new();
+    let mut run_start = 0;
+    for (i, line) in content.lines().enumerate() {
+        match uncomment(family, line, i == 0) {
+            Some(text) => {
+                if run.is_empty() {
+                    run_start = i;
+                }
+                run.push(text);
+            }
+            None => {
+                blocks.extend(find_code(&mut parser, family, &run, run_start, min_lines));
+                run.clear();
+            }
+        }
+    }
+    blocks.extend(find_code(&mut parser, family, &run, run_start, min_lines));
+    blocks
+}
+
+/// Comment markers of full-line comments
+fn markers(family: Family) -> &'static [&'static str] {
+    match family {
+        Family::Python | Family::Ruby => &["#"],
+        Family::Js | Family::Rust | Family::Go => &["//"],
+        Family::Php => &["//", "#"],
+        _ => &[],
+    }
+}
+
+/// The text of a full-line comment, without its marker and the space after
+/// it; `None` for code, blank lines and comments that are never code
+fn uncomment(family: Family, line: &str, first: bool) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let never_code = trimmed.starts_with("///")
+        || trimmed.starts_with("//!")
+        || (first && trimmed.starts_with("#!"))
+        || (family == Family::Php && trimmed.starts_with("#["));
+    if never_code {
+        return None;
+    }
+    let marker = markers(family).iter().find(|m| trimmed.starts_with(*m))?;
+    let text = &trimmed[marker.len()..];
+    Some(text.strip_prefix(' ').unwrap_or(text))
+}
+
+/// The code block in a run of comments starting at 0-based line `start`,
+/// dropping leading prose lines until the rest parses
+fn find_code(
+    parser: &mut Parser,
+    family: Family,
+    run: &[&str],
+    start: usize,
+    min_lines: usize,
+) -> Option<Block> {
+    let last = run.iter().rposition(|l| !l.trim().is_empty())?;
+    let run = &run[..=last];
+    (0..run.len())
+        .take_while(|&skip| non_empty(&run[skip..]) >= min_lines)
+        .find(|&skip| !run[skip].trim().is_empty() && is_code(parser, family, &run[skip..]))
+        .map(|skip| Block {
+            start: start + skip + 1,
+            end: start + run.len(),
+        })
+}
+
+fn non_empty(lines: &[&str]) -> usize {
+    lines.iter().filter(|l| !l.trim().is_empty()).count()
+}
+
+/// Whether `lines` are code that parses cleanly in `family`'s grammar
+fn is_code(parser: &mut Parser, family: Family, lines: &[&str]) -> bool {
+    let code_like = lines
+        .iter()
+        .filter(|l| l.contains(['(', ')', '[', ']', '{', '}', '=', ';']))
+        .count();
+    if code_like * 2 < non_empty(lines) {
+        return false;
+    }
+
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let mut source = String::new();
+    if family == Family::Php {
+        source.push_str("<?php\n");
+    }
+    for line in lines {
+        source.push_str(line.get(indent..).unwrap_or(line.trim_start()));
+        source.push('\n');
+    }
+
+    let Some(tree) = parser.parse(&source, None) else {
+        return false;
+    };
+    let root = tree.root_node();
+    !root.has_error() && root.named_child_count() > 0
+}
@@ -4,11 +4,17 @@
 //! deprecated modules, circular dependency workarounds, and unpinned versions.
 //! Targets: `.py`, `.java`, `.ts`, `.js`, `.tsx`, `.jsx`, `package.json`,
 //! `requirements.txt`, `Cargo.toml`, `pyproject.toml`.
+//!
+//! The deprecated-module check only applies to files whose Python target is
+//! 3.12 or later (see [`crate::language_target`]); on older targets those
+//! modules still import, and the `UPGRADE` analyzer reports them.
 
 use crate::analyzer::{make_finding, Analyzer};
 use crate::config::RevetConfig;
 use crate::finding::{Finding, FixKind, Severity};
+use crate::language_target::{Language, LanguageTargets};
 use regex::Regex;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
@@ -24,6 +30,9 @@ struct DepPattern {
     /// Exact filenames this pattern targets (e.g., "package.json")
     target_filenames: &'static [&'static str],
     suggestion: &'static str,
+    /// If set, the pattern only applies to files whose target for the
+    /// language is at least this version
+    min_target: Option<(Language, &'static str)>,
     fix_kind: FixKind,
 }
 
@@ -42,6 +51,7 @@ fn patterns() -> &'static [DepPattern] {
                 target_extensions: &["py"],
                 target_filenames: &[],
                 suggestion: "Import specific names instead of using wildcard import",
+                min_target: None,
                 fix_kind: FixKind::Suggestion,
             },
             // Pattern 2: Wildcard import (Java)
@@ -53,6 +63,7 @@ fn patterns() -> &'static [DepPattern] {
                 target_extensions: &["java"],
                 target_filenames: &[],
                 suggestion: "Import specific classes instead of using wildcard import",
+                min_target: None,
                 fix_kind: FixKind::Suggestion,
             },
             // Pattern 3: Deprecated Python import
@@ -66,6 +77,7 @@ fn patterns() -> &'static [DepPattern] {
                 target_extensions: &["py"],
                 target_filenames: &[],
                 suggestion: "This module is deprecated/removed in Python 3.12+; use its modern replacement",
+                min_target: Some((Language::Python, "3.12")),
                 fix_kind: FixKind::Suggestion,
             },
             // Pattern 6: Circular import workaround
@@ -79,6 +91,7 @@ fn patterns() -> &'static [DepPattern] {
                 target_extensions: &["py", "ts", "js", "tsx", "jsx"],
                 target_filenames: &[],
                 suggestion: "Resolve the circular dependency instead of suppressing the lint",
+                min_target: None,
                 fix_kind: FixKind::Suggestion,
             },
             // Pattern 7: Unpinned/wildcard dep version
@@ -90,6 +103,7 @@ fn patterns() -> &'static [DepPattern] {
                 target_extensions: &[],
                 target_filenames: &["package.json"],
                 suggestion: "Pin dependency to a specific version or semver range",
+                min_target: None,
                 fix_kind: FixKind::Suggestion,
             },
             // ── Info: best practice ──────────────────────────────────────
@@ -102,6 +116,7 @@ fn patterns() -> &'static [DepPattern] {
                 target_extensions: &["ts", "js", "tsx", "jsx"],
                 target_filenames: &[],
                 suggestion: "Use ES module import syntax instead of require()",
+                min_target: None,
                 fix_kind: FixKind::Suggestion,
             },
             // Pattern 5: Deeply nested relative import (3+)
@@ -115,6 +130,7 @@ fn patterns() -> &'static [DepPattern] {
                 target_extensions: &["py", "ts", "js", "tsx", "jsx"],
                 target_filenames: &[],
                 suggestion: "Use absolute imports or path aliases instead of deep relative imports",
+                min_target: None,
                 fix_kind: FixKind::Suggestion,
             },
             // Pattern 8: Git dependency
@@ -128,6 +144,7 @@ fn patterns() -> &'static [DepPattern] {
                 target_extensions: &["toml"],
                 target_filenames: &["package.json", "requirements.txt"],
                 suggestion: "Use a published package version instead of a git dependency",
+                min_target: None,
                 fix_kind: FixKind::Suggestion,
             },
         ]
@@ -154,12 +171,23 @@ const BINARY_EXTENSIONS: &[&str] = &[
 ];
 
 /// Analyzer that detects dependency hygiene issues
-pub struct DependencyAnalyzer;
+pub struct DependencyAnalyzer {
+    /// `[targets]`
+    targets: BTreeMap<String, String>,
+}
 
 impl DependencyAnalyzer {
     /// Create a new dependency analyzer
     pub fn new() -> Self {
-        Self
+        Self {
+            targets: BTreeMap::new(),
+        }
+    }
+
+    /// Take the language targets `[targets]` sets over manifests
+    pub fn with_targets(mut self, targets: &BTreeMap<String, String>) -> Self {
+        self.targets = targets.clone();
+        self
     }
 
     /// Check if a file should be scanned (must match dependency file types, not binary)
@@ -214,19 +242,26 @@ impl DependencyAnalyzer {
     }
 
     /// Scan a single file for dependency patterns
-    fn scan_file(path: &Path) -> Vec<Finding> {
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return Vec::new(),
-        };
+    fn scan_file(path: &Path, targets: &mut LanguageTargets) -> Vec<Finding> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::scan_source(path, &content, targets),
+            Err(_) => Vec::new(),
+        }
+    }
 
+    fn scan_source(path: &Path, content: &str, targets: &mut LanguageTargets) -> Vec<Finding> {
         let all_patterns = patterns();
         let mut findings = Vec::new();
 
-        // Filter patterns applicable to this file
+        // Filter patterns applicable to this file and its targets
         let applicable: Vec<&DepPattern> = all_patterns
             .iter()
             .filter(|p| Self::pattern_matches_file(p, path))
+            .filter(|p| {
+                p.min_target.is_none_or(|(language, version)| {
+                    targets.for_file(language, path).at_least(version)
+                })
+            })
             .collect();
 
         if applicable.is_empty() {
@@ -286,19 +321,40 @@ impl Analyzer for DependencyAnalyzer {
         config.modules.dependency
     }
 
-    fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
+    fn module(&self) -> Option<&'static str> {
+        Some("dependency")
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Self::should_scan(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], repo_root: &Path) -> Vec<Finding> {
+        let mut targets = LanguageTargets::new(repo_root, &self.targets);
         let mut findings = Vec::new();
 
         for file in files {
             if !Self::should_scan(file) {
                 continue;
             }
-            findings.extend(Self::scan_file(file));
+            findings.extend(Self::scan_file(file, &mut targets));
         }
 
         findings
     }
 
+    fn analyze_source(&self, path: &Path, content: &str, repo_root: &Path) -> Vec<Finding> {
+        if !Self::should_scan(path) {
+            return Vec::new();
+        }
+        let mut targets = LanguageTargets::new(repo_root, &self.targets);
+        Self::scan_source(path, content, &mut targets)
+    }
+
     fn extra_extensions(&self) -> &[&str] {
         &[".toml", ".txt", ".json"]
     }
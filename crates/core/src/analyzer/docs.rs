@@ -0,0 +1,568 @@
+//! Documentation analyzer (`DOC`) — public symbols without doc comments
+//!
+//! The parsers that support it record on each node whether a documentation
+//! comment precedes the declaration (see [`crate::parser::docs`]); nodes of
+//! other parsers stay unrecorded and are left out, so the analyzer covers a
+//! language exactly when its parser reports doc-comment capability.
+//!
+//! A symbol counts when it is part of the public surface, as each language
+//! spells it:
+//!
+//! - **Rust**: declared `pub`; trait members of a public trait
+//! - **Go**: a capitalized name (and receiver type)
+//! - **TypeScript/JavaScript**: `export`ed; class members unless `private`,
+//!   `protected` or `#`-named
+//! - **Python**: no leading underscore, and listed in `__all__` when the
+//!   module has one
+//! - **Java/C#**: declared `public`; interface members
+//!
+//! Members count only when their class or interface does; symbols nested in
+//! functions never do. Trivial symbols are skipped by configurable
+//! heuristics: getters (`get…`/`is…`/`has…` without parameters, `@property`,
+//! C# properties, one-line accessors of the same-named field), constants
+//! (declared constant or `ALL_CAPS`) and type aliases that merely re-export
+//! another type. Re-exports proper (`pub use`, `export … from`) are imports
+//! and never counted.
+//!
+//! Each undocumented symbol is an `undocumented` finding (`info` by
+//! default). Coverage — documented over counted symbols — is computed per
+//! file and per `[[project]]` (the whole repository without any); with
+//! `[analyzers.docs] min_coverage` set, each project below it gets a
+//! `coverage` finding (`error` by default). Test, generated and
+//! `[[vendored]]` files are left out.
+
+use super::coupling::Scope;
+use super::{catalog_finding, GraphAnalyzer};
+use crate::config::{DocsConfig, RevetConfig};
+use crate::finding::{Finding, Severity};
+use crate::graph::{CodeGraph, Node, NodeData, NodeId, NodeKind};
+use crate::msg;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// `[modules]` name of the analyzer
+pub const MODULE: &str = "docs";
+
+/// Sub-rules, as used in `[analyzers.docs]` `only`/`disable` and `severity`
+pub const RULES: &[&str] = &["undocumented", "coverage"];
+
+/// Analyzer that reports undocumented public symbols
+pub struct DocsAnalyzer;
+
+impl Default for DocsAnalyzer {
+    fn default() -> Self {
+        Self
+    }
+}
+
+impl DocsAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Documented symbols out of those counted
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Coverage {
+    pub documented: usize,
+    pub public: usize,
+    /// `documented / public` as a percentage; 100 without public symbols
+    pub percent: f64,
+}
+
+impl Coverage {
+    pub fn new(documented: usize, public: usize) -> Self {
+        let percent = if public == 0 {
+            100.0
+        } else {
+            documented as f64 * 100.0 / public as f64
+        };
+        Self {
+            documented,
+            public,
+            percent,
+        }
+    }
+
+    fn add(self, documented: bool) -> Self {
+        Self::new(self.documented + usize::from(documented), self.public + 1)
+    }
+}
+
+impl Default for Coverage {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
+/// Coverage of one file or project, by its path relative to the root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PathCoverage {
+    pub path: String,
+    #[serde(flatten)]
+    pub coverage: Coverage,
+}
+
+/// Documentation coverage of a run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DocCoverage {
+    #[serde(flatten)]
+    pub total: Coverage,
+    /// Per `[[project]]`, or `.` for the whole repository without any
+    #[serde(default)]
+    pub projects: Vec<PathCoverage>,
+    /// Files with public symbols, least covered first
+    #[serde(default)]
+    pub files: Vec<PathCoverage>,
+}
+
+/// A counted symbol and whether it is documented
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub node: NodeId,
+    pub documented: bool,
+}
+
+/// What [`survey`] found: the counted symbols and their coverage
+#[derive(Debug, Clone, Default)]
+pub struct Survey {
+    pub symbols: Vec<Symbol>,
+    pub coverage: DocCoverage,
+}
+
+/// Node kinds whose documentation is checked
+fn is_documentable(kind: &NodeKind) -> bool {
+    matches!(
+        kind,
+        NodeKind::Function
+            | NodeKind::Class
+            | NodeKind::Interface
+            | NodeKind::Type
+            | NodeKind::Variable
+    )
+}
+
+/// Count the public symbols of every included file of `graph`
+pub fn survey(graph: &CodeGraph, config: &RevetConfig) -> Survey {
+    let settings = &config.analyzers.docs;
+    let mut scope = Scope::new(graph, config);
+    let mut languages: HashMap<&Path, &str> = HashMap::new();
+    let mut by_file: BTreeMap<&Path, Vec<NodeId>> = BTreeMap::new();
+    for (id, node) in graph.nodes() {
+        match node.data() {
+            NodeData::File { language, .. } => {
+                languages.insert(node.file_path(), language);
+            }
+            _ if is_documentable(node.kind()) && node.doc().is_recorded() => {
+                by_file.entry(node.file_path()).or_default().push(id);
+            }
+            _ => {}
+        }
+    }
+
+    let root = graph.root_path();
+    let projects: Vec<&str> = if config.projects.is_empty() {
+        vec!["."]
+    } else {
+        config
+            .projects
+            .iter()
+            .map(|p| p.path.trim_start_matches("./").trim_end_matches('/'))
+            .collect()
+    };
+    let mut survey = Survey::default();
+    let mut project_totals: BTreeMap<&str, Coverage> =
+        projects.iter().map(|p| (*p, Coverage::default())).collect();
+    for (file, ids) in by_file {
+        if !scope.includes(file) {
+            continue;
+        }
+        let Some(language) = languages.get(file) else {
+            continue;
+        };
+        let source = std::fs::read_to_string(file).unwrap_or_default();
+        let lines: Vec<&str> = source.lines().collect();
+        let symbols = public_symbols(graph, &ids, language, &lines, settings);
+        if symbols.is_empty() {
+            continue;
+        }
+
+        let mut coverage = Coverage::default();
+        for symbol in &symbols {
+            coverage = coverage.add(symbol.documented);
+            survey.coverage.total = survey.coverage.total.add(symbol.documented);
+        }
+        let rel = relative(root, file);
+        let project = projects
+            .iter()
+            .filter(|p| **p == "." || rel == **p || rel.starts_with(&format!("{}/", p)))
+            .max_by_key(|p| p.len());
+        if let Some(total) = project.and_then(|p| project_totals.get_mut(p)) {
+            *total = Coverage::new(
+                total.documented + coverage.documented,
+                total.public + coverage.public,
+            );
+        }
+        survey.coverage.files.push(PathCoverage {
+            path: rel,
+            coverage,
+        });
+        survey.symbols.extend(symbols);
+    }
+
+    survey.coverage.files.sort_by(|a, b| {
+        a.coverage
+            .percent
+            .total_cmp(&b.coverage.percent)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    survey.coverage.projects = project_totals
+        .into_iter()
+        .map(|(path, coverage)| PathCoverage {
+            path: path.to_string(),
+            coverage,
+        })
+        .collect();
+    survey
+}
+
+/// Coverage of `graph`, for the run summary
+pub fn coverage(graph: &CodeGraph, config: &RevetConfig) -> DocCoverage {
+    survey(graph, config).coverage
+}
+
+/// The counted symbols among `ids`, all of one file in `language`, whose
+/// source is `lines`, in line order
+pub fn public_symbols(
+    graph: &CodeGraph,
+    ids: &[NodeId],
+    language: &str,
+    lines: &[&str],
+    settings: &DocsConfig,
+) -> Vec<Symbol> {
+    let nodes: Vec<(NodeId, &Node)> = ids
+        .iter()
+        .filter_map(|&id| graph.node(id).map(|n| (id, n)))
+        .collect();
+    let exports = (language == "python").then(|| dunder_all(lines)).flatten();
+    let file = Surface {
+        nodes: &nodes,
+        language,
+        lines,
+        exports: exports.as_ref(),
+    };
+
+    let mut symbols: Vec<Symbol> = nodes
+        .iter()
+        .filter(|(_, node)| file.is_public(node) && !is_trivial(node, language, lines, settings))
+        .map(|&(id, node)| Symbol {
+            node: id,
+            documented: node.doc().summary().is_some(),
+        })
+        .collect();
+    symbols.sort_by_key(|s| graph.node(s.node).map(Node::line));
+    symbols
+}
+
+/// The declarations of one file, and what makes them public
+struct Surface<'a> {
+    nodes: &'a [(NodeId, &'a Node)],
+    language: &'a str,
+    lines: &'a [&'a str],
+    /// Names in a Python module's `__all__`, when it has one
+    exports: Option<&'a HashSet<String>>,
+}
+
+impl Surface<'_> {
+    /// Whether `node` and everything enclosing it is public
+    fn is_public(&self, node: &Node) -> bool {
+        let container = self.container(node);
+        if let Some(container) = container {
+            if !matches!(container.kind(), NodeKind::Class | NodeKind::Interface)
+                || !self.is_public(container)
+            {
+                return false;
+            }
+        } else if let Some((receiver, _)) = node.name().rsplit_once('.') {
+            // A Go method on a type declared in another file
+            if self.language == "go" && !is_capitalized(receiver) {
+                return false;
+            }
+        }
+        let in_interface = container.is_some_and(|c| c.kind() == &NodeKind::Interface);
+        let name = short_name(node.name());
+        let decl = declaration(node, self.lines);
+        match self.language {
+            "rust" => in_interface || decl.starts_with("pub ") || decl.starts_with("pub("),
+            "go" => is_capitalized(name),
+            "python" => {
+                !name.starts_with('_')
+                    && (container.is_some() || self.exports.is_none_or(|all| all.contains(name)))
+            }
+            "typescript" if container.is_some() => {
+                !(name.starts_with('#')
+                    || decl.starts_with("private ")
+                    || decl.starts_with("protected "))
+            }
+            "typescript" => decl.starts_with("export "),
+            "java" | "csharp" => in_interface || has_word(decl, "public"),
+            _ => node.is_public(),
+        }
+    }
+
+    /// The innermost declaration enclosing `node`: the one its qualified
+    /// name names, or else the one whose lines contain it
+    fn container(&self, node: &Node) -> Option<&Node> {
+        if let Some((parent, _)) = node.name().rsplit_once('.') {
+            return self
+                .nodes
+                .iter()
+                .map(|(_, n)| *n)
+                .find(|n| n.name() == parent && n.kind() != &NodeKind::Function);
+        }
+        self.nodes
+            .iter()
+            .map(|(_, n)| *n)
+            .filter(|n| !std::ptr::eq(*n, node))
+            .filter(|n| {
+                n.line() < node.line() && n.end_line().is_some_and(|end| end >= node.line())
+            })
+            .max_by_key(|n| n.line())
+    }
+}
+
+/// Whether `node` is trivial enough to go without documentation under the
+/// `skip_*` heuristics of `settings`
+fn is_trivial(node: &Node, language: &str, lines: &[&str], settings: &DocsConfig) -> bool {
+    let name = short_name(node.name());
+    match node.data() {
+        NodeData::Function { parameters, .. } if settings.skip_getters => {
+            // Go methods list their receiver first
+            let receiver = usize::from(language == "go" && node.name().contains('.'));
+            let takes_nothing = parameters
+                .iter()
+                .skip(receiver)
+                .all(|p| matches!(p.name.as_str(), "self" | "&self" | "&mut self" | "cls"));
+            let decl = declaration(node, lines);
+            let prefixed = ["get", "is", "has", "Get", "Is", "Has"]
+                .iter()
+                .any(|prefix| {
+                    name.strip_prefix(prefix).is_some_and(|rest| {
+                        rest.starts_with(|c: char| c == '_' || c.is_uppercase())
+                    })
+                });
+            let accessor = node.end_line() == Some(node.line())
+                && (decl.contains(&format!("self.{}", name))
+                    || decl.contains(&format!("this.{}", name)));
+            node.decorators().iter().any(|d| d == "property")
+                || decl.contains("{ get")
+                || takes_nothing && (prefixed || accessor)
+        }
+        NodeData::Variable { is_constant, .. } if settings.skip_constants => {
+            *is_constant || is_all_caps(name)
+        }
+        NodeData::Type { definition } if settings.skip_reexports => {
+            let target = definition.rsplit('=').next().unwrap_or(definition);
+            let target = target.trim().trim_end_matches(';');
+            !target.is_empty()
+                && target
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '_' | ':' | '.'))
+        }
+        _ => false,
+    }
+}
+
+/// The line declaring `node`: the first of its lines naming it, past any
+/// attributes and decorators, trimmed
+fn declaration<'a>(node: &Node, lines: &[&'a str]) -> &'a str {
+    let name = short_name(node.name());
+    let start = node.line().saturating_sub(1);
+    let end = node.end_line().unwrap_or(node.line()).min(start + 20);
+    (start..end.max(start + 1))
+        .filter_map(|i| lines.get(i))
+        .find(|l| l.contains(name))
+        .or_else(|| lines.get(start))
+        .map_or("", |l| l.trim())
+}
+
+/// Names listed in a Python module's `__all__`
+fn dunder_all(lines: &[&str]) -> Option<HashSet<String>> {
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("__all__") && l.contains('='))?;
+    let mut names = HashSet::new();
+    for line in &lines[start..] {
+        for (i, part) in line.split(['"', '\'']).enumerate() {
+            if i % 2 == 1 {
+                names.insert(part.to_string());
+            }
+        }
+        if line.contains(']') || line.contains(')') {
+            break;
+        }
+    }
+    Some(names)
+}
+
+fn short_name(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+fn is_capitalized(name: &str) -> bool {
+    short_name(name)
+        .chars()
+        .next()
+        .is_some_and(char::is_uppercase)
+}
+
+fn is_all_caps(name: &str) -> bool {
+    name.chars().any(char::is_alphabetic)
+        && name
+            .chars()
+            .all(|c| c.is_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Whether `text` has `word` as a whole word
+fn has_word(text: &str, word: &str) -> bool {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .any(|w| w == word)
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn severity(config: &DocsConfig, rule: &str) -> Severity {
+    match config.severity.get(rule).map(String::as_str) {
+        Some("error") => Severity::Error,
+        Some("warning") => Severity::Warning,
+        Some("info") => Severity::Info,
+        _ if rule == "coverage" => Severity::Error,
+        _ => Severity::Info,
+    }
+}
+
+fn undocumented(node: &Node, settings: &DocsConfig) -> Finding {
+    Finding {
+        rule: Some("undocumented".to_string()),
+        ..catalog_finding(
+            severity(settings, "undocumented"),
+            msg!("docs-undocumented", name = node.name()),
+            node.file_path().clone(),
+            node.line(),
+            Some(msg!("docs-undocumented.suggestion")),
+            None,
+        )
+    }
+}
+
+impl GraphAnalyzer for DocsAnalyzer {
+    fn name(&self) -> &str {
+        "Documentation"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "DOC"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.docs
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some(MODULE)
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn analyze_graph(&self, graph: &CodeGraph, config: &RevetConfig) -> Vec<Finding> {
+        let settings = &config.analyzers.docs;
+        let survey = survey(graph, config);
+        let mut findings: Vec<Finding> = survey
+            .symbols
+            .iter()
+            .filter(|s| !s.documented)
+            .filter_map(|s| graph.node(s.node))
+            .map(|node| undocumented(node, settings))
+            .collect();
+
+        let Some(min) = settings.min_coverage else {
+            return findings;
+        };
+        for project in &survey.coverage.projects {
+            let coverage = project.coverage;
+            if coverage.percent >= min * 100.0 {
+                continue;
+            }
+            let file = if project.path == "." {
+                PathBuf::new()
+            } else {
+                graph.root_path().join(&project.path)
+            };
+            findings.push(Finding {
+                rule: Some("coverage".to_string()),
+                ..catalog_finding(
+                    severity(settings, "coverage"),
+                    msg!(
+                        "docs-coverage",
+                        project = &project.path,
+                        percent = format!("{:.1}", coverage.percent),
+                        documented = coverage.documented,
+                        public = coverage.public,
+                        min = format!("{:.0}", min * 100.0)
+                    ),
+                    file,
+                    0,
+                    Some(msg!("docs-coverage.suggestion")),
+                    None,
+                )
+            });
+        }
+        findings
+    }
+
+    fn analyze_file(
+        &self,
+        graph: &CodeGraph,
+        file: &Path,
+        content: &str,
+        config: &RevetConfig,
+    ) -> Vec<Finding> {
+        let settings = &config.analyzers.docs;
+        let mut language = None;
+        let mut ids = Vec::new();
+        for id in graph.find_nodes(file, None) {
+            let Some(node) = graph.node(id).filter(|n| n.file_path() == file) else {
+                continue;
+            };
+            match node.data() {
+                NodeData::File { language: l, .. } => language = Some(l.as_str()),
+                _ if is_documentable(node.kind()) && node.doc().is_recorded() => ids.push(id),
+                _ => {}
+            }
+        }
+        let (Some(language), false) = (language, ids.is_empty()) else {
+            return Vec::new();
+        };
+        if !Scope::new(graph, config).includes(file) {
+            return Vec::new();
+        }
+        let lines: Vec<&str> = content.lines().collect();
+        public_symbols(graph, &ids, language, &lines, settings)
+            .into_iter()
+            .filter(|s| !s.documented)
+            .filter_map(|s| graph.node(s.node))
+            .map(|node| undocumented(node, settings))
+            .collect()
+    }
+}
@@ -10,14 +10,21 @@
 //! Enabled via `[modules] duplication = true` in `.revet.toml`.
 //! Threshold: `duplication_min_lines` (default: 6).
 
-use crate::analyzer::Analyzer;
+use crate::analyzer::{file_timing, Analyzer};
+use crate::calibrate::Metric;
 use crate::config::RevetConfig;
-use crate::finding::{Finding, Severity};
+use crate::finding::{Finding, FindingDetails, Severity};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 
+/// Shortest duplicate block reported
+const MIN_LINES: usize = 3;
+
+/// Longest block length `measure` tries; longer clones count as this long
+const MAX_MEASURED_LINES: usize = 50;
+
 pub struct DuplicationAnalyzer {
     min_lines: usize,
 }
@@ -29,7 +36,7 @@ impl DuplicationAnalyzer {
 
     pub fn with_min_lines(min_lines: usize) -> Self {
         Self {
-            min_lines: min_lines.max(3),
+            min_lines: min_lines.max(MIN_LINES),
         }
     }
 }
@@ -53,35 +60,65 @@ impl Analyzer for DuplicationAnalyzer {
         config.modules.duplication
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("duplication")
+    }
+
+    fn accepts(&self, _path: &Path) -> bool {
+        true
+    }
+
     fn analyze_files(&self, files: &[PathBuf], repo_root: &Path) -> Vec<Finding> {
         self.detect(files, repo_root, self.min_lines)
     }
+
+    /// Clone sizes: a duplicate block's size is the longest
+    /// `duplication_min_lines` it is still reported at. The findings of each
+    /// candidate come from running detection at that length.
+    fn measure(&self, files: &[PathBuf], repo_root: &Path) -> Vec<Metric> {
+        let sources: Vec<Source> = files.iter().filter_map(|f| normalize_file(f)).collect();
+        let mut sizes: HashMap<(PathBuf, usize), usize> = HashMap::new();
+        let mut candidates = Vec::new();
+        for min_lines in MIN_LINES..=MAX_MEASURED_LINES {
+            let findings = Self::duplicates(&sources, repo_root, min_lines);
+            for finding in &findings {
+                sizes.insert((finding.file.clone(), finding.line), min_lines);
+            }
+            candidates.push((min_lines as f64, findings.len()));
+            if findings.is_empty() {
+                break;
+            }
+        }
+        let mut values: Vec<f64> = sizes.into_values().map(|n| n as f64).collect();
+        values.sort_by(f64::total_cmp);
+        vec![Metric {
+            setting: "modules.duplication_min_lines",
+            label: "lines per duplicate block",
+            decimals: 0,
+            current: self.min_lines as f64,
+            values,
+            candidates,
+        }]
+    }
 }
 
+/// Normalized non-blank lines of one file, with their 1-indexed numbers
+type Source = (PathBuf, Vec<(usize, String)>);
+
 impl DuplicationAnalyzer {
     fn detect(&self, files: &[PathBuf], repo_root: &Path, min_lines: usize) -> Vec<Finding> {
+        let sources: Vec<Source> = files
+            .iter()
+            .filter_map(|file| file_timing::time_file(file, || normalize_file(file)))
+            .collect();
+        Self::duplicates(&sources, repo_root, min_lines)
+    }
+
+    fn duplicates(sources: &[Source], repo_root: &Path, min_lines: usize) -> Vec<Finding> {
         // Map: hash → list of (file, start_line, preview)
         let mut buckets: HashMap<u64, Vec<(PathBuf, usize, String)>> = HashMap::new();
 
-        for file in files {
-            let content = match std::fs::read_to_string(file) {
-                Ok(c) => c,
-                Err(_) => continue,
-            };
-
-            let normalized: Vec<(usize, String)> = content
-                .lines()
-                .enumerate()
-                .filter_map(|(i, line)| {
-                    let norm = normalize_line(line);
-                    if norm.is_empty() {
-                        None
-                    } else {
-                        Some((i + 1, norm)) // 1-indexed line numbers
-                    }
-                })
-                .collect();
-
+        for (file, normalized) in sources {
             if normalized.len() < min_lines {
                 continue;
             }
@@ -130,10 +167,13 @@ impl DuplicationAnalyzer {
                 id: String::new(), // renumbered by dispatcher
                 severity: Severity::Info,
                 message: format!(
-                    "Duplicate block ({} line{}) also found at: {}",
+                    "Duplicate block ({} line{}) also found at {}",
                     min_lines,
                     if min_lines == 1 { "" } else { "s" },
-                    others.join(", ")
+                    match &others[..] {
+                        [other] => other.clone(),
+                        _ => format!("{} other locations", others.len()),
+                    }
                 ),
                 file: primary_file.clone(),
                 line: *primary_line,
@@ -142,6 +182,10 @@ impl DuplicationAnalyzer {
                     truncate(preview, 40)
                 )),
                 affected_dependents: others.len(),
+                details: Some(FindingDetails {
+                    location_list: others,
+                    ..Default::default()
+                }),
                 ..Default::default()
             });
 
@@ -153,6 +197,24 @@ impl DuplicationAnalyzer {
     }
 }
 
+/// Read `file` and normalize its lines; `None` when it can't be read
+fn normalize_file(file: &Path) -> Option<Source> {
+    let content = std::fs::read_to_string(file).ok()?;
+    let normalized = content
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let norm = normalize_line(line);
+            if norm.is_empty() {
+                None
+            } else {
+                Some((i + 1, norm)) // 1-indexed line numbers
+            }
+        })
+        .collect();
+    Some((file.to_path_buf(), normalized))
+}
+
 // ── Normalization ─────────────────────────────────────────────────────────────
 
 /// Normalize a source line for duplicate detection:
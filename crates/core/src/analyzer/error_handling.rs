@@ -4,16 +4,20 @@
 //! files line-by-line for patterns like empty catch blocks, bare except, unwrap chains,
 //! and swallowed errors. Only one finding per line (first matching pattern wins).
 
-use crate::analyzer::{make_finding, Analyzer};
-use crate::config::RevetConfig;
+use crate::analyzer::{catalog_finding, Analyzer};
+use crate::config::{RevetConfig, RuleSelection};
 use crate::finding::{Finding, FixKind, Severity};
+use crate::i18n::Msg;
 use regex::Regex;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 /// A compiled error handling pattern detection rule
 struct ErrorPattern {
-    name: &'static str,
+    /// Sub-rule id (`error_handling:<rule>`)
+    rule: &'static str,
+    /// Catalog key for the message; the suggestion is its `.suggestion` attribute
+    message_key: &'static str,
     regex: Regex,
     severity: Severity,
     /// If set, skip the match when the line contains this substring
@@ -22,7 +26,7 @@ struct ErrorPattern {
     extensions: &'static [&'static str],
     /// If true, skip this pattern when the file is a test file
     skip_in_test_files: bool,
-    suggestion: &'static str,
+    suggestion_key: &'static str,
     fix_kind: FixKind,
 }
 
@@ -33,51 +37,56 @@ fn patterns() -> &'static [ErrorPattern] {
         vec![
             // ERR-001: Empty catch/except block (multi-language)
             ErrorPattern {
-                name: "Empty catch/except block",
+                rule: "empty_catch",
+                message_key: "error-handling-empty-catch",
                 regex: Regex::new(r"(?:catch\s*(?:\([^)]*\))?\s*\{\s*\}|except[^:]*:\s*(?:pass\s*$))").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: None,
                 extensions: &[],
                 skip_in_test_files: false,
-                suggestion: "Handle the error or add a comment explaining why it is safe to ignore",
+                suggestion_key: "error-handling-empty-catch.suggestion",
                 fix_kind: FixKind::Suggestion,
             },
             // ERR-002: Bare except: (no exception type) in Python
             ErrorPattern {
-                name: "Bare except without exception type",
+                rule: "bare_except",
+                message_key: "error-handling-bare-except",
                 regex: Regex::new(r"^\s*except\s*:").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: None,
                 extensions: &["py"],
                 skip_in_test_files: false,
-                suggestion: "Specify an exception type: except ValueError: or except Exception as e:",
+                suggestion_key: "error-handling-bare-except.suggestion",
                 fix_kind: FixKind::Suggestion,
             },
             // ERR-003: .unwrap() in Rust (skip test files)
             ErrorPattern {
-                name: ".unwrap() call",
+                rule: "unwrap",
+                message_key: "error-handling-unwrap",
                 regex: Regex::new(r"\.unwrap\(\)").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: None,
                 extensions: &["rs"],
                 skip_in_test_files: true,
-                suggestion: "Use ? operator, .unwrap_or(), .unwrap_or_else(), or .expect() with a descriptive message",
+                suggestion_key: "error-handling-unwrap.suggestion",
                 fix_kind: FixKind::Suggestion,
             },
             // ERR-004: panic!()/todo!()/unimplemented!() in non-test Rust code
             ErrorPattern {
-                name: "panic!/todo!/unimplemented! in non-test code",
+                rule: "panic",
+                message_key: "error-handling-panic",
                 regex: Regex::new(r"\b(?:panic!|todo!|unimplemented!)\s*\(").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: None,
                 extensions: &["rs"],
                 skip_in_test_files: true,
-                suggestion: "Return a Result with a descriptive error instead of panicking",
+                suggestion_key: "error-handling-panic.suggestion",
                 fix_kind: FixKind::Suggestion,
             },
             // ERR-005: .expect() with a non-descriptive message in Rust
             ErrorPattern {
-                name: ".expect() with non-descriptive message",
+                rule: "expect_message",
+                message_key: "error-handling-expect-message",
                 regex: Regex::new(
                     r#"\.expect\s*\(\s*["'](?:|error|err|failed|fail|failure|oops|todo|fixme|ok|bad|wrong|none|panic|crash|broken|invalid|unexpected|unreachable|missing|unknown|test|no|yes|x|temp|hack)\s*["']\s*\)"#,
                 )
@@ -86,51 +95,55 @@ fn patterns() -> &'static [ErrorPattern] {
                 reject_if_contains: None,
                 extensions: &["rs"],
                 skip_in_test_files: true,
-                suggestion: "Use a descriptive message: .expect(\"Failed to open config file\")",
+                suggestion_key: "error-handling-expect-message.suggestion",
                 fix_kind: FixKind::Suggestion,
             },
             // ERR-006: Catch that only logs (swallowed error)
             ErrorPattern {
-                name: "Catch block only logs error",
+                rule: "swallowed",
+                message_key: "error-handling-swallowed",
                 regex: Regex::new(r"catch\s*\([^)]*\)\s*\{\s*(?:console\.(?:log|warn|error|info)|System\.(?:out|err)\.print|log(?:ger)?\.(?:error|warn|info|debug))\s*\(").unwrap(),
                 severity: Severity::Info,
                 reject_if_contains: Some("throw"),
                 extensions: &["js", "ts", "jsx", "tsx", "java"],
                 skip_in_test_files: false,
-                suggestion: "Re-throw the error or handle it properly after logging",
+                suggestion_key: "error-handling-swallowed.suggestion",
                 fix_kind: FixKind::Suggestion,
             },
             // ERR-007: except Exception or except BaseException (too broad)
             ErrorPattern {
-                name: "Too-broad exception catch",
+                rule: "broad_except",
+                message_key: "error-handling-broad-except",
                 regex: Regex::new(r"^\s*except\s+(?:Exception|BaseException)\b").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: None,
                 extensions: &["py"],
                 skip_in_test_files: false,
-                suggestion: "Catch a more specific exception type (e.g. ValueError, KeyError)",
+                suggestion_key: "error-handling-broad-except.suggestion",
                 fix_kind: FixKind::Suggestion,
             },
             // ERR-008: Empty .catch() callback in JS/TS
             ErrorPattern {
-                name: "Empty .catch() callback",
+                rule: "empty_promise_catch",
+                message_key: "error-handling-empty-promise-catch",
                 regex: Regex::new(r"\.catch\s*\(\s*(?:\(\s*[^)]*\)\s*=>\s*\{\s*\}|\w+\s*=>\s*\{\s*\}|\(\s*\)\s*\{\s*\}|function\s*\(\s*[^)]*\)\s*\{\s*\})\s*\)").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: None,
                 extensions: &["js", "ts", "jsx", "tsx"],
                 skip_in_test_files: false,
-                suggestion: "Handle or re-throw the error in the .catch() callback",
+                suggestion_key: "error-handling-empty-promise-catch.suggestion",
                 fix_kind: FixKind::Suggestion,
             },
             // ERR-009: Discarded error in Go (_ = err)
             ErrorPattern {
-                name: "Discarded error in Go",
+                rule: "discarded_error",
+                message_key: "error-handling-discarded-error",
                 regex: Regex::new(r"_\s*=\s*err\b").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: None,
                 extensions: &["go"],
                 skip_in_test_files: false,
-                suggestion: "Handle the error: if err != nil { return err }",
+                suggestion_key: "error-handling-discarded-error.suggestion",
                 fix_kind: FixKind::Suggestion,
             },
         ]
@@ -142,12 +155,34 @@ const ERROR_EXTENSIONS: &[&str] = &[
     "py", "js", "ts", "jsx", "tsx", "rs", "go", "java", "kt", "cs",
 ];
 
+/// Sub-rule ids, in pattern order
+const RULES: &[&str] = &[
+    "empty_catch",
+    "bare_except",
+    "unwrap",
+    "panic",
+    "expect_message",
+    "swallowed",
+    "broad_except",
+    "empty_promise_catch",
+    "discarded_error",
+];
+
 /// Analyzer that detects error handling anti-patterns
-pub struct ErrorHandlingAnalyzer;
+pub struct ErrorHandlingAnalyzer {
+    rules: RuleSelection,
+}
 
 impl ErrorHandlingAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self::with_rules(&RuleSelection::default())
+    }
+
+    /// Create an analyzer that only tries the sub-rules `rules` selects
+    pub fn with_rules(rules: &RuleSelection) -> Self {
+        Self {
+            rules: rules.clone(),
+        }
     }
 
     /// Check if a file should be scanned based on its extension
@@ -187,12 +222,14 @@ impl ErrorHandlingAnalyzer {
     }
 
     /// Scan a single file for error handling issues
-    fn scan_file(path: &Path) -> Vec<Finding> {
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return Vec::new(),
-        };
+    fn scan_file(&self, path: &Path) -> Vec<Finding> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => self.scan_source(path, &content),
+            Err(_) => Vec::new(),
+        }
+    }
 
+    fn scan_source(&self, path: &Path, content: &str) -> Vec<Finding> {
         let all_patterns = patterns();
         let is_test = Self::is_test_file(path);
         let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
@@ -243,6 +280,9 @@ impl ErrorHandlingAnalyzer {
 
             // First matching pattern wins for this line
             for pat in all_patterns.iter() {
+                if !self.rules.allows(pat.rule) {
+                    continue;
+                }
                 // Extension gate
                 if !pat.extensions.is_empty() && !pat.extensions.contains(&ext) {
                     continue;
@@ -259,14 +299,17 @@ impl ErrorHandlingAnalyzer {
                         }
                     }
 
-                    findings.push(make_finding(
-                        pat.severity,
-                        pat.name.to_string(),
-                        path.to_path_buf(),
-                        line_num + 1,
-                        Some(pat.suggestion.to_string()),
-                        Some(pat.fix_kind.clone()),
-                    ));
+                    findings.push(Finding {
+                        rule: Some(pat.rule.to_string()),
+                        ..catalog_finding(
+                            pat.severity,
+                            Msg::new(pat.message_key),
+                            path.to_path_buf(),
+                            line_num + 1,
+                            Some(Msg::new(pat.suggestion_key)),
+                            Some(pat.fix_kind.clone()),
+                        )
+                    });
                     break;
                 }
             }
@@ -295,6 +338,22 @@ impl Analyzer for ErrorHandlingAnalyzer {
         config.modules.error_handling
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("error_handling")
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Self::should_scan(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
     fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
         let mut findings = Vec::new();
 
@@ -302,9 +361,16 @@ impl Analyzer for ErrorHandlingAnalyzer {
             if !Self::should_scan(file) {
                 continue;
             }
-            findings.extend(Self::scan_file(file));
+            findings.extend(self.scan_file(file));
         }
 
         findings
     }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        if !Self::should_scan(path) {
+            return Vec::new();
+        }
+        self.scan_source(path, content)
+    }
 }
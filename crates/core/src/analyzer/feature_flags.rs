@@ -0,0 +1,283 @@
+//! Feature-flags analyzer — detects checks of flags that are launched or
+//! retired, flags missing from the inventory, and inventory entries no code
+//! checks any more
+//!
+//! Checks are the literal flag names passed to the flag-check calls of the
+//! configured SDKs (see [`checks`]); the inventory is `.revet/flags.toml` (see
+//! [`inventory`]), or the file `[analyzers.feature_flags] inventory` names.
+//!
+//! - `stale_flag`: a check of a launched or retired flag, reported at the
+//!   check
+//! - `dead_branch`: the branch of an `if` on a stale flag that can no longer
+//!   run — the `else` of a launched flag, the body of a retired one
+//! - `unknown_flag`: a checked flag the inventory does not list
+//! - `unused_flag`: an active or launched inventory entry no check names.
+//!   Retired entries are the record of finished cleanups and are left alone
+//! - `dynamic_flag`: a flag name built at runtime, which cannot be checked.
+//!   Its literal prefix (`checkout-` in `"checkout-" + region`) keeps matching
+//!   entries from being reported as unused
+//! - `invalid_inventory`: an inventory that cannot be parsed; nothing else is
+//!   reported until it is fixed
+//!
+//! Without an inventory file nothing is reported. Only the files of the run
+//! are scanned, so `unused_flag` is accurate on `--full` runs.
+//!
+//! Finding prefix: `FLAG-`
+
+pub mod checks;
+pub mod inventory;
+
+use crate::analyzer::{catalog_finding, Analyzer};
+use crate::config::{FeatureFlagsConfig, RevetConfig};
+use crate::finding::{Confidence, Finding, Severity};
+use crate::msg;
+use checks::{Check, CheckTable, FlagName};
+use inventory::{Flag, Inventory, State};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Dynamic flag expressions longer than this are shortened in messages
+const MAX_EXPR: usize = 60;
+
+/// Analyzer that checks feature-flag checks against the flag inventory
+pub struct FeatureFlagsAnalyzer {
+    inventory: PathBuf,
+    table: CheckTable,
+}
+
+impl FeatureFlagsAnalyzer {
+    /// Create a feature-flags analyzer with the default settings
+    pub fn new() -> Self {
+        Self::with_config(&FeatureFlagsConfig::default())
+    }
+
+    /// Create a feature-flags analyzer honouring `[analyzers.feature_flags]`
+    pub fn with_config(config: &FeatureFlagsConfig) -> Self {
+        Self {
+            inventory: PathBuf::from(&config.inventory),
+            table: CheckTable::new(&config.sdks, &config.functions),
+        }
+    }
+}
+
+impl Default for FeatureFlagsAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer for FeatureFlagsAnalyzer {
+    fn name(&self) -> &str {
+        "Feature flags"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "FLAG"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.feature_flags
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some("feature_flags")
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        &[
+            "stale_flag",
+            "dead_branch",
+            "unknown_flag",
+            "unused_flag",
+            "dynamic_flag",
+            "invalid_inventory",
+        ]
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        checks::supports(path)
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], repo_root: &Path) -> Vec<Finding> {
+        // Without an inventory there is nothing to check flags against
+        let inventory = match Inventory::read(&repo_root.join(&self.inventory)) {
+            Ok(Some(inventory)) => inventory,
+            Ok(None) => return Vec::new(),
+            Err(e) => {
+                return vec![Finding {
+                    rule: Some("invalid_inventory".to_string()),
+                    ..catalog_finding(
+                        Severity::Warning,
+                        msg!("flags-invalid-inventory", error = &e.message),
+                        e.file,
+                        e.line,
+                        Some(msg!("flags-invalid-inventory.suggestion")),
+                        None,
+                    )
+                }]
+            }
+        };
+
+        let mut findings = Vec::new();
+        let mut used: HashSet<&str> = HashSet::new();
+        let mut prefixes: Vec<String> = Vec::new();
+        let mut reported = HashSet::new();
+        for file in files.iter().filter(|f| checks::supports(f)) {
+            let Ok(content) = std::fs::read_to_string(file) else {
+                continue;
+            };
+            for check in checks::scan(file, &content, &self.table) {
+                match &check.flag {
+                    FlagName::Literal(name) => {
+                        if !reported.insert((file.clone(), check.line, name.clone())) {
+                            continue;
+                        }
+                        match inventory.get(name) {
+                            Some(flag) => {
+                                used.insert(flag.name.as_str());
+                                findings.extend(stale(file, &check, flag));
+                            }
+                            None => {
+                                findings.push(unknown_flag(file, check.line, name, &self.inventory))
+                            }
+                        }
+                    }
+                    FlagName::Dynamic { prefix, expr } => {
+                        prefixes.push(prefix.clone());
+                        findings.push(dynamic_flag(file, check.line, expr));
+                    }
+                }
+            }
+        }
+
+        for flag in &inventory.flags {
+            let referenced = used.contains(flag.name.as_str())
+                || prefixes
+                    .iter()
+                    .any(|p| !p.is_empty() && flag.name.starts_with(p.as_str()));
+            if referenced || flag.state == State::Retired {
+                continue;
+            }
+            findings.push(Finding {
+                rule: Some("unused_flag".to_string()),
+                confidence: Confidence::Medium,
+                ..catalog_finding(
+                    Severity::Info,
+                    msg!("flags-unused", flag = &flag.name),
+                    inventory.file.clone(),
+                    flag.line,
+                    Some(msg!("flags-unused.suggestion")),
+                    None,
+                )
+            });
+        }
+        findings
+    }
+}
+
+/// `stale_flag` and `dead_branch` findings for a check of `flag`
+fn stale(file: &Path, check: &Check, flag: &Flag) -> Vec<Finding> {
+    let name = flag.name.as_str();
+    let (message, suggestion, dead) = match (flag.state, &flag.since) {
+        (State::Active, _) => return Vec::new(),
+        (State::Launched, Some(since)) => (
+            msg!("flags-stale-launched-since", flag = name, since = since),
+            msg!("flags-stale-launched.suggestion"),
+            check.guard.as_ref().and_then(|g| g.off),
+        ),
+        (State::Launched, None) => (
+            msg!("flags-stale-launched", flag = name),
+            msg!("flags-stale-launched.suggestion"),
+            check.guard.as_ref().and_then(|g| g.off),
+        ),
+        (State::Retired, Some(since)) => (
+            msg!("flags-stale-retired-since", flag = name, since = since),
+            msg!("flags-stale-retired.suggestion"),
+            check.guard.as_ref().and_then(|g| g.on),
+        ),
+        (State::Retired, None) => (
+            msg!("flags-stale-retired", flag = name),
+            msg!("flags-stale-retired.suggestion"),
+            check.guard.as_ref().and_then(|g| g.on),
+        ),
+    };
+    let mut findings = vec![Finding {
+        rule: Some("stale_flag".to_string()),
+        ..catalog_finding(
+            Severity::Warning,
+            message,
+            file.to_path_buf(),
+            check.line,
+            Some(suggestion),
+            None,
+        )
+    }];
+    if let Some((start, end)) = dead {
+        let (message, suggestion) = match flag.state {
+            State::Retired => (
+                msg!(
+                    "flags-dead-branch-retired",
+                    flag = name,
+                    start = start,
+                    end = end
+                ),
+                msg!("flags-dead-branch-retired.suggestion", line = check.line),
+            ),
+            _ => (
+                msg!(
+                    "flags-dead-branch-launched",
+                    flag = name,
+                    start = start,
+                    end = end
+                ),
+                msg!("flags-dead-branch-launched.suggestion", line = check.line),
+            ),
+        };
+        findings.push(Finding {
+            rule: Some("dead_branch".to_string()),
+            ..catalog_finding(
+                Severity::Info,
+                message,
+                file.to_path_buf(),
+                start,
+                Some(suggestion),
+                None,
+            )
+        });
+    }
+    findings
+}
+
+fn unknown_flag(file: &Path, line: usize, name: &str, inventory: &Path) -> Finding {
+    let inventory = inventory.to_string_lossy();
+    Finding {
+        rule: Some("unknown_flag".to_string()),
+        ..catalog_finding(
+            Severity::Info,
+            msg!("flags-unknown", flag = name, inventory = &inventory),
+            file.to_path_buf(),
+            line,
+            Some(msg!("flags-unknown.suggestion")),
+            None,
+        )
+    }
+}
+
+fn dynamic_flag(file: &Path, line: usize, expr: &str) -> Finding {
+    let expr: String = expr.split_whitespace().collect::<Vec<_>>().join(" ");
+    let expr = match expr.char_indices().nth(MAX_EXPR) {
+        Some((end, _)) => format!("{}…", &expr[..end]),
+        None => expr,
+    };
+    Finding {
+        rule: Some("dynamic_flag".to_string()),
+        ..catalog_finding(
+            Severity::Info,
+            msg!("flags-dynamic", expr = expr),
+            file.to_path_buf(),
+            line,
+            Some(msg!("flags-dynamic.suggestion")),
+            None,
+        )
+    }
+}
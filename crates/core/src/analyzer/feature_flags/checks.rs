@@ -0,0 +1,384 @@
+//! Flag checks in source code
+//!
+//! Python, JavaScript/TypeScript, Go and Java sources are parsed with
+//! tree-sitter and every call whose callee is in the check table has its
+//! first argument inspected. A string literal (or a template without
+//! substitutions) is a flag name; anything else is dynamic, keeping the
+//! literal text it starts with (`"checkout-" + region` → `checkout-`).
+//!
+//! A check that is the whole condition of an `if`, possibly negated and
+//! parenthesized, records the line ranges of the branches it chooses between.
+
+use crate::parser::grammar::{language_for, Family, Grammar};
+use std::collections::HashMap;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Flag-check calls of one feature-flag SDK
+pub struct Sdk {
+    pub name: &'static str,
+    /// Callees whose first argument is a flag name, per language. A leading
+    /// dot matches the method on any receiver (`.variation` matches
+    /// `ld.variation` and `this.client.variation`); a dotted name also
+    /// matches with a receiver in front
+    pub calls: &'static [(Family, &'static [&'static str])],
+}
+
+/// Built-in check table
+pub const SDKS: &[Sdk] = &[
+    Sdk {
+        name: "launchdarkly",
+        calls: &[
+            (
+                Family::Js,
+                &[
+                    ".variation",
+                    ".variationDetail",
+                    ".boolVariation",
+                    ".stringVariation",
+                    ".numberVariation",
+                    ".jsonVariation",
+                ],
+            ),
+            (Family::Python, &[".variation", ".variation_detail"]),
+            (
+                Family::Go,
+                &[
+                    ".BoolVariation",
+                    ".StringVariation",
+                    ".IntVariation",
+                    ".Float64Variation",
+                    ".JSONVariation",
+                    ".BoolVariationDetail",
+                ],
+            ),
+            (
+                Family::Java,
+                &[
+                    ".boolVariation",
+                    ".stringVariation",
+                    ".intVariation",
+                    ".doubleVariation",
+                    ".jsonValueVariation",
+                    ".boolVariationDetail",
+                ],
+            ),
+        ],
+    },
+    Sdk {
+        name: "unleash",
+        calls: &[
+            (Family::Js, &[".isEnabled", ".getVariant"]),
+            (Family::Python, &[".is_enabled", ".get_variant"]),
+            (Family::Go, &["unleash.IsEnabled", "unleash.GetVariant"]),
+            (Family::Java, &[".isEnabled", ".getVariant"]),
+        ],
+    },
+    Sdk {
+        name: "openfeature",
+        calls: &[
+            (
+                Family::Js,
+                &[
+                    ".getBooleanValue",
+                    ".getStringValue",
+                    ".getNumberValue",
+                    ".getObjectValue",
+                ],
+            ),
+            (
+                Family::Python,
+                &[
+                    ".get_boolean_value",
+                    ".get_string_value",
+                    ".get_integer_value",
+                    ".get_float_value",
+                    ".get_object_value",
+                ],
+            ),
+            (
+                Family::Java,
+                &[
+                    ".getBooleanValue",
+                    ".getStringValue",
+                    ".getIntegerValue",
+                    ".getDoubleValue",
+                    ".getObjectValue",
+                ],
+            ),
+        ],
+    },
+];
+
+/// The flag a check names
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FlagName {
+    Literal(String),
+    /// Built at runtime; `prefix` is the literal text it starts with
+    Dynamic {
+        prefix: String,
+        expr: String,
+    },
+}
+
+/// Line ranges an `if` on a flag check chooses between
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guard {
+    /// Lines that run when the flag is on
+    pub on: Option<(usize, usize)>,
+    /// Lines that run when the flag is off
+    pub off: Option<(usize, usize)>,
+}
+
+/// One flag-check site
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub flag: FlagName,
+    pub line: usize,
+    pub guard: Option<Guard>,
+}
+
+/// Callees selected by `[analyzers.feature_flags]`
+#[derive(Debug, Clone, Default)]
+pub struct CheckTable {
+    names: HashMap<Family, Vec<String>>,
+}
+
+impl CheckTable {
+    /// Table for the named SDKs plus extra callees for every language
+    pub fn new(sdks: &[String], functions: &[String]) -> Self {
+        let mut table = Self::default();
+        for sdk in SDKS.iter().filter(|s| sdks.iter().any(|n| n == s.name)) {
+            for (family, calls) in sdk.calls {
+                table
+                    .names(*family)
+                    .extend(calls.iter().map(|c| c.to_string()));
+            }
+        }
+        for family in [Family::Python, Family::Js, Family::Go, Family::Java] {
+            table.names(family).extend(functions.iter().cloned());
+        }
+        table
+    }
+
+    fn names(&mut self, family: Family) -> &mut Vec<String> {
+        self.names.entry(family).or_default()
+    }
+
+    fn matches(&self, family: Family, callee: &str) -> bool {
+        let Some(names) = self.names.get(&family) else {
+            return false;
+        };
+        names.iter().any(|name| {
+            if name.starts_with('.') {
+                return callee.len() > name.len() && callee.ends_with(name.as_str());
+            }
+            callee == name
+                || (name.contains('.')
+                    && callee
+                        .strip_suffix(name.as_str())
+                        .is_some_and(|receiver| receiver.ends_with('.')))
+        })
+    }
+}
+
+/// Whether `path` is scanned for flag checks
+pub fn supports(path: &Path) -> bool {
+    grammar(path).is_some()
+}
+
+fn grammar(path: &Path) -> Option<Grammar> {
+    language_for(path).filter(|g| {
+        matches!(
+            g.family,
+            Family::Python | Family::Js | Family::Go | Family::Java
+        )
+    })
+}
+
+/// Every flag check in one source, in source order
+pub fn scan(path: &Path, content: &str, table: &CheckTable) -> Vec<Check> {
+    let Some(grammar) = grammar(path) else {
+        return Vec::new();
+    };
+    let Some(tree) = grammar.parse(content) else {
+        return Vec::new();
+    };
+    let mut scanner = Scanner {
+        family: grammar.family,
+        source: content.as_bytes(),
+        table,
+        checks: Vec::new(),
+    };
+    scanner.walk(tree.root_node());
+    scanner.checks
+}
+
+struct Scanner<'a> {
+    family: Family,
+    source: &'a [u8],
+    table: &'a CheckTable,
+    checks: Vec<Check>,
+}
+
+impl<'a> Scanner<'a> {
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.source).unwrap_or("")
+    }
+
+    fn walk(&mut self, node: Node) {
+        if matches!(
+            node.kind(),
+            "call_expression" | "call" | "method_invocation"
+        ) {
+            self.call(node);
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.walk(child);
+        }
+    }
+
+    /// Callee text without whitespace: `ld.variation`, `client.isEnabled`
+    fn callee(&self, node: Node) -> Option<String> {
+        let text = if node.kind() == "method_invocation" {
+            let name = self.text(node.child_by_field_name("name")?);
+            match node.child_by_field_name("object") {
+                Some(object) => format!("{}.{}", self.text(object), name),
+                None => name.to_string(),
+            }
+        } else {
+            self.text(node.child_by_field_name("function")?).to_string()
+        };
+        Some(text.chars().filter(|c| !c.is_whitespace()).collect())
+    }
+
+    fn call(&mut self, node: Node) {
+        let (Some(callee), Some(args)) = (self.callee(node), node.child_by_field_name("arguments"))
+        else {
+            return;
+        };
+        if !self.table.matches(self.family, &callee) {
+            return;
+        }
+        let mut cursor = args.walk();
+        let first = args
+            .named_children(&mut cursor)
+            .find(|a| !matches!(a.kind(), "comment" | "keyword_argument"));
+        let Some(arg) = first else {
+            return;
+        };
+        self.checks.push(Check {
+            flag: self.flag(arg),
+            line: node.start_position().row + 1,
+            guard: guard(node, self.source),
+        });
+    }
+
+    fn flag(&self, node: Node) -> FlagName {
+        if let Some(literal) = self.literal(node) {
+            return FlagName::Literal(literal);
+        }
+        FlagName::Dynamic {
+            prefix: self.prefix(node),
+            expr: self.text(node).to_string(),
+        }
+    }
+
+    /// The value of a string literal without interpolation
+    fn literal(&self, node: Node) -> Option<String> {
+        match node.kind() {
+            "string" if !has_child(node, "interpolation") => {
+                Some(unquote(self.text(node)).to_string())
+            }
+            "template_string" if !has_child(node, "template_substitution") => {
+                Some(unquote(self.text(node)).to_string())
+            }
+            "interpreted_string_literal" | "raw_string_literal" | "string_literal"
+                if !has_child(node, "string_interpolation") =>
+            {
+                Some(unquote(self.text(node)).to_string())
+            }
+            "parenthesized_expression" => self.literal(node.named_child(0)?),
+            _ => None,
+        }
+    }
+
+    /// Literal text a dynamic flag name starts with
+    fn prefix(&self, node: Node) -> String {
+        match node.kind() {
+            "binary_expression" | "binary_operator" => node
+                .child_by_field_name("left")
+                .map(|left| self.literal(left).unwrap_or_else(|| self.prefix(left)))
+                .unwrap_or_default(),
+            "template_string" => {
+                let text = self.text(node);
+                let body = text.strip_prefix('`').unwrap_or(text);
+                body.split("${").next().unwrap_or("").to_string()
+            }
+            "string" => {
+                let text = unquote(self.text(node));
+                text.split('{').next().unwrap_or("").to_string()
+            }
+            _ => String::new(),
+        }
+    }
+}
+
+/// The branches of the `if` whose whole condition is `call`
+fn guard(call: Node, source: &[u8]) -> Option<Guard> {
+    let mut node = call;
+    let mut negated = false;
+    loop {
+        let parent = node.parent()?;
+        match parent.kind() {
+            "parenthesized_expression" => {}
+            "not_operator" => negated = !negated,
+            "unary_expression"
+                if parent
+                    .child_by_field_name("operator")
+                    .and_then(|op| op.utf8_text(source).ok())
+                    == Some("!") =>
+            {
+                negated = !negated
+            }
+            "if_statement" if parent.child_by_field_name("condition") == Some(node) => {
+                let taken = parent.child_by_field_name("consequence").map(lines);
+                // Python lists each `elif`/`else` as an alternative; the rest
+                // of the statement after the first one is the other branch
+                let other = parent
+                    .child_by_field_name("alternative")
+                    .map(|alt| (alt.start_position().row + 1, parent.end_position().row + 1));
+                let (on, off) = if negated {
+                    (other, taken)
+                } else {
+                    (taken, other)
+                };
+                return Some(Guard { on, off });
+            }
+            _ => return None,
+        }
+        node = parent;
+    }
+}
+
+fn lines(node: Node) -> (usize, usize) {
+    (node.start_position().row + 1, node.end_position().row + 1)
+}
+
+fn has_child(node: Node, kind: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node.named_children(&mut cursor).any(|c| c.kind() == kind);
+    found
+}
+
+/// Strip string prefixes (`f`, `u`, `r`, `b`) and quotes
+fn unquote(text: &str) -> &str {
+    let text = text.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    for quote in ["\"\"\"", "'''", "\"", "'", "`"] {
+        if let Some(inner) = text.strip_prefix(quote).and_then(|t| t.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    text
+}
@@ -0,0 +1,136 @@
+//! The flag inventory: every flag the team knows about and its state
+//!
+//! ```toml
+//! [[flag]]
+//! name    = "checkout-v2"
+//! state   = "launched"    # active | launched | retired
+//! owner   = "payments"
+//! created = 2023-11-02
+//! since   = 2024-03-01    # when the flag reached its state
+//! ```
+//!
+//! Dates may be TOML dates or strings; only `name` and `state` are required.
+//! The file is maintained by hand or exported from the flag system.
+
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use toml::Spanned;
+
+/// Lifecycle state of a flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum State {
+    /// Still being rolled out or experimented with
+    Active,
+    /// On for everyone; the check and its fallback are dead weight
+    Launched,
+    /// Off for everyone and archived in the flag system
+    Retired,
+}
+
+/// One inventory entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flag {
+    pub name: String,
+    pub state: State,
+    pub owner: Option<String>,
+    pub created: Option<String>,
+    /// When the flag reached its current state
+    pub since: Option<String>,
+    /// Line of the entry's `name`
+    pub line: usize,
+}
+
+/// A parsed inventory file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inventory {
+    pub file: PathBuf,
+    pub flags: Vec<Flag>,
+}
+
+/// Why an inventory file could not be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InventoryError {
+    pub file: PathBuf,
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+struct RawInventory {
+    #[serde(default)]
+    flag: Vec<RawFlag>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawFlag {
+    name: Spanned<String>,
+    state: State,
+    owner: Option<String>,
+    created: Option<toml::Value>,
+    since: Option<toml::Value>,
+}
+
+impl Inventory {
+    /// Read the inventory at `path`. `Ok(None)` when the file does not exist.
+    pub fn read(path: &Path) -> Result<Option<Self>, InventoryError> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::parse(path.to_path_buf(), &content).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Parse inventory `content` read from `file`
+    pub fn parse(file: PathBuf, content: &str) -> Result<Self, InventoryError> {
+        let error = |offset: usize, message: String| InventoryError {
+            file: file.clone(),
+            line: line_at(content, offset),
+            message,
+        };
+        let raw: RawInventory = toml::from_str(content).map_err(|e| {
+            let offset = e.span().map(|s| s.start).unwrap_or(0);
+            error(offset, e.message().trim().to_string())
+        })?;
+
+        let mut flags: Vec<Flag> = Vec::with_capacity(raw.flag.len());
+        for entry in raw.flag {
+            let offset = entry.name.span().start;
+            let name = entry.name.into_inner();
+            if name.trim().is_empty() {
+                return Err(error(offset, "flag name must not be empty".to_string()));
+            }
+            if flags.iter().any(|f| f.name == name) {
+                return Err(error(offset, format!("flag {:?} is listed twice", name)));
+            }
+            flags.push(Flag {
+                name,
+                state: entry.state,
+                owner: entry.owner,
+                created: entry.created.as_ref().and_then(date),
+                since: entry.since.as_ref().and_then(date),
+                line: line_at(content, offset),
+            });
+        }
+        Ok(Self { file, flags })
+    }
+
+    /// The entry for `name`
+    pub fn get(&self, name: &str) -> Option<&Flag> {
+        self.flags.iter().find(|f| f.name == name)
+    }
+}
+
+/// A TOML date or string as text
+fn date(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::Datetime(d) => Some(d.to_string()),
+        toml::Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        _ => None,
+    }
+}
+
+/// 1-based line of byte `offset`
+fn line_at(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
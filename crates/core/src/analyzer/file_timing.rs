@@ -0,0 +1,268 @@
+//! Per-file time and allocation accounting (`--timings=full`,
+//! `[performance] slow_file_warn_ms`)
+//!
+//! The dispatcher turns the per-file clock on around an analyzer batch when
+//! asked to. Per-file analyzers need nothing: the dispatcher hands them one
+//! file at a time and times each call. Analyzers that look at the files
+//! together wrap the work on each one in [`time_file`]. With the clock off,
+//! [`time_file`] only runs its closure, so the accounting costs a
+//! thread-local read per file.
+
+use crate::analyzer::{catalog_finding, AnalyzerTiming};
+use crate::finding::{Finding, Severity};
+use crate::msg;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Files listed per analyzer in `--timings=full`
+pub const SLOWEST_FILES: usize = 10;
+
+/// Time one analyzer spent on one file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileTiming {
+    pub file: PathBuf,
+    pub duration: Duration,
+}
+
+/// The per-file clock of the batch running on this thread
+#[derive(Default)]
+struct Clock {
+    files: Vec<FileTiming>,
+    /// A file is being timed; nested [`time_file`] calls only run
+    busy: bool,
+}
+
+thread_local! {
+    static CLOCK: RefCell<Option<Clock>> = const { RefCell::new(None) };
+}
+
+/// Run `f`, the work of an analyzer on `file`, charging its time to `file`
+/// when the per-file clock is on
+pub fn time_file<T>(file: &Path, f: impl FnOnce() -> T) -> T {
+    let timed = CLOCK.with(|clock| match clock.borrow_mut().as_mut() {
+        Some(clock) if !clock.busy => {
+            clock.busy = true;
+            true
+        }
+        _ => false,
+    });
+    if !timed {
+        return f();
+    }
+    let start = Instant::now();
+    let out = f();
+    let duration = start.elapsed();
+    CLOCK.with(|clock| {
+        if let Some(clock) = clock.borrow_mut().as_mut() {
+            clock.busy = false;
+            clock.files.push(FileTiming {
+                file: file.to_path_buf(),
+                duration,
+            });
+        }
+    });
+    out
+}
+
+/// What one analyzer invocation cost
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Batch {
+    pub duration: Duration,
+    pub files: Vec<FileTiming>,
+    pub peak_alloc: Option<u64>,
+}
+
+impl Batch {
+    /// Add a later batch of the same analyzer (a partition)
+    pub fn extend(&mut self, other: Batch) {
+        self.duration += other.duration;
+        self.files.extend(other.files);
+        self.peak_alloc = match (self.peak_alloc, other.peak_alloc) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+    }
+
+    /// The batch as an [`AnalyzerTiming`], keeping the slowest files and
+    /// the ones at or over `threshold`
+    pub fn timing(
+        mut self,
+        name: &str,
+        prefix: &str,
+        findings: usize,
+        threshold: Option<Duration>,
+    ) -> AnalyzerTiming {
+        self.files.sort_by(|a, b| {
+            b.duration
+                .cmp(&a.duration)
+                .then_with(|| a.file.cmp(&b.file))
+        });
+        let mut rank = 0;
+        self.files.retain(|f| {
+            rank += 1;
+            rank <= SLOWEST_FILES || threshold.is_some_and(|t| f.duration >= t)
+        });
+        AnalyzerTiming {
+            name: name.to_string(),
+            prefix: prefix.to_string(),
+            duration: self.duration,
+            findings,
+            files: self.files,
+            peak_alloc: self.peak_alloc,
+        }
+    }
+}
+
+/// Run `f`, one analyzer batch, on this thread: with the per-file clock on
+/// when `timed`, and measuring its allocations in `alloc-stats` builds
+pub(crate) fn batch<T>(timed: bool, f: impl FnOnce() -> T) -> (T, Batch) {
+    if timed {
+        CLOCK.with(|clock| *clock.borrow_mut() = Some(Clock::default()));
+    }
+    let start = Instant::now();
+    #[cfg(feature = "alloc-stats")]
+    let (out, peak_alloc) = {
+        let (out, peak) = crate::alloc_stats::measure(f);
+        (out, Some(peak))
+    };
+    #[cfg(not(feature = "alloc-stats"))]
+    let (out, peak_alloc) = (f(), None);
+    let duration = start.elapsed();
+    let files = if timed {
+        CLOCK
+            .with(|clock| clock.borrow_mut().take())
+            .map(|clock| clock.files)
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    (
+        out,
+        Batch {
+            duration,
+            files,
+            peak_alloc,
+        },
+    )
+}
+
+/// Info findings (`PERF`) for the files an analyzer spent at least
+/// `threshold` on, one per file naming the slowest analyzer
+pub fn slow_files<'a>(
+    timings: impl IntoIterator<Item = &'a AnalyzerTiming>,
+    threshold: Duration,
+) -> Vec<Finding> {
+    let mut slowest: BTreeMap<&Path, (&str, Duration)> = BTreeMap::new();
+    for timing in timings {
+        for file in timing.files.iter().filter(|f| f.duration >= threshold) {
+            let entry = slowest
+                .entry(file.file.as_path())
+                .or_insert((timing.name.as_str(), file.duration));
+            if file.duration > entry.1 {
+                *entry = (timing.name.as_str(), file.duration);
+            }
+        }
+    }
+    slowest
+        .into_iter()
+        .map(|(file, (analyzer, duration))| {
+            catalog_finding(
+                Severity::Info,
+                msg!(
+                    "performance-slow-file",
+                    analyzer = analyzer,
+                    ms = duration.as_millis() as u64,
+                    max = threshold.as_millis() as u64
+                ),
+                file.to_path_buf(),
+                1,
+                Some(msg!("performance-slow-file.suggestion")),
+                None,
+            )
+        })
+        .collect()
+}
+
+/// `--timings` in the JSON summary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TimingReport {
+    pub total_ms: f64,
+    pub analyzers: Vec<AnalyzerReport>,
+    /// With `[cache.remote]` configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_cache: Option<crate::cache::remote::RemoteStats>,
+    /// What the analyzer memo did, in a watch or daemon session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<crate::cache::memo::MemoStats>,
+}
+
+/// One analyzer's line in [`TimingReport`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AnalyzerReport {
+    pub name: String,
+    pub prefix: String,
+    pub ms: f64,
+    pub findings: usize,
+    /// Slowest first, with `--timings=full`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub slowest_files: Vec<FileReport>,
+    /// In `alloc-stats` builds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_alloc_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FileReport {
+    /// Relative to the repository root
+    pub file: String,
+    pub ms: f64,
+}
+
+impl TimingReport {
+    /// The report of `timings`, listing the slowest files when `full`
+    pub fn new<'a>(
+        timings: impl IntoIterator<Item = &'a AnalyzerTiming>,
+        repo_root: &Path,
+        full: bool,
+    ) -> Self {
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        let analyzers: Vec<AnalyzerReport> = timings
+            .into_iter()
+            .map(|t| AnalyzerReport {
+                name: t.name.clone(),
+                prefix: t.prefix.clone(),
+                ms: ms(t.duration),
+                findings: t.findings,
+                slowest_files: if full {
+                    t.slowest_files()
+                        .iter()
+                        .map(|f| FileReport {
+                            file: f
+                                .file
+                                .strip_prefix(repo_root)
+                                .unwrap_or(&f.file)
+                                .to_string_lossy()
+                                .replace('\\', "/"),
+                            ms: ms(f.duration),
+                        })
+                        .collect()
+                } else {
+                    Vec::new()
+                },
+                peak_alloc_bytes: t.peak_alloc,
+            })
+            .collect();
+        Self {
+            total_ms: analyzers.iter().map(|a| a.ms).sum(),
+            analyzers,
+            remote_cache: None,
+            memo: None,
+        }
+    }
+}
@@ -0,0 +1,469 @@
+//! Generated code analyzer — detects drift between a spec and the code generated from it
+//!
+//! Each `[[analyzers.generated]]` entry pairs a spec (OpenAPI document,
+//! `.proto` file, ...) with the directory its client is generated into. The
+//! first run records a content hash of the spec and of the generated tree in
+//! `hash_file`; later runs compare against that record:
+//!
+//! - spec changed, tree unchanged → stale generated code (Warning)
+//! - tree changed, spec unchanged → generated files edited by hand (Warning)
+//! - both changed → the client was regenerated; the record is updated
+//!
+//! Hashes are platform-independent: files are visited in sorted path order,
+//! paths use `/` separators, and CRLF line endings are normalised to LF before
+//! hashing. Delete an entry from the hash file to accept the current state.
+//!
+//! The generation command only runs under `--fix` when the entry sets
+//! `allow_regenerate = true` (see [`regenerate`]).
+
+use crate::analyzer::{make_finding, Analyzer};
+use crate::audit::{self, MutationJournal};
+use crate::config::{GeneratedCodeConfig, RevetConfig};
+use crate::finding::{Finding, FixKind, Severity};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Drifted files listed in a finding message before truncating
+const MAX_LISTED_FILES: usize = 10;
+
+// ── Hash records ──────────────────────────────────────────────────────────────
+
+/// Contents of a hash file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HashFile {
+    #[serde(default)]
+    generated: Vec<HashRecord>,
+}
+
+/// Recorded state of one spec / output pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HashRecord {
+    spec: String,
+    output_dir: String,
+    spec_hash: String,
+    tree_hash: String,
+    /// Per-file hashes keyed by path relative to `output_dir`
+    files: BTreeMap<String, String>,
+}
+
+impl HashFile {
+    fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = self.render()?;
+        std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn render(&self) -> Result<String> {
+        let body = toml::to_string(self)?;
+        Ok(format!(
+            "# Recorded by revet for the GEN analyzer — commit this file.\n\
+             # Remove an entry to accept the current spec and generated code.\n\n{}",
+            body
+        ))
+    }
+
+    fn find(&self, spec: &str, output_dir: &str) -> Option<&HashRecord> {
+        self.generated
+            .iter()
+            .find(|r| r.spec == spec && r.output_dir == output_dir)
+    }
+
+    fn upsert(&mut self, record: HashRecord) {
+        self.generated
+            .retain(|r| !(r.spec == record.spec && r.output_dir == record.output_dir));
+        self.generated.push(record);
+        self.generated
+            .sort_by(|a, b| (&a.spec, &a.output_dir).cmp(&(&b.spec, &b.output_dir)));
+    }
+}
+
+/// Current hashes of a spec / output pair
+struct Snapshot {
+    spec_hash: String,
+    tree_hash: String,
+    files: BTreeMap<String, String>,
+}
+
+impl Snapshot {
+    fn take(repo_root: &Path, spec: &str, output_dir: &str, hash_file: &Path) -> Result<Self> {
+        let spec_path = repo_root.join(spec);
+        let spec_bytes = std::fs::read(&spec_path)
+            .with_context(|| format!("Failed to read spec {}", spec_path.display()))?;
+        let files = hash_tree(&repo_root.join(output_dir), hash_file)?;
+        Ok(Self {
+            spec_hash: hash_content(&spec_bytes),
+            tree_hash: tree_hash(&files),
+            files,
+        })
+    }
+
+    fn into_record(self, spec: &str, output_dir: &str) -> HashRecord {
+        HashRecord {
+            spec: spec.to_string(),
+            output_dir: output_dir.to_string(),
+            spec_hash: self.spec_hash,
+            tree_hash: self.tree_hash,
+            files: self.files,
+        }
+    }
+}
+
+/// SHA-256 of `bytes` with CRLF line endings normalised to LF
+pub fn hash_content(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    let mut rest = bytes;
+    while let Some(pos) = rest.windows(2).position(|w| w == b"\r\n") {
+        hasher.update(&rest[..pos]);
+        // Drop the `\r`, keep the `\n`
+        rest = &rest[pos + 1..];
+    }
+    hasher.update(rest);
+    hex(&hasher.finalize())
+}
+
+/// Hash every regular file under `dir`, keyed by `/`-separated relative path
+fn hash_tree(dir: &Path, skip: &Path) -> Result<BTreeMap<String, String>> {
+    hash_tree_with(dir, skip, hash_content)
+}
+
+/// [`hash_tree`] with another hash of the file contents
+fn hash_tree_with(
+    dir: &Path,
+    skip: &Path,
+    hash: fn(&[u8]) -> String,
+) -> Result<BTreeMap<String, String>> {
+    let mut files = BTreeMap::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        for entry in std::fs::read_dir(&current)
+            .with_context(|| format!("Failed to read {}", current.display()))?
+        {
+            let path = entry?.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else if path.is_file() && path != skip {
+                let rel = path.strip_prefix(dir).unwrap_or(&path);
+                let key = rel
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.insert(key, hash(&std::fs::read(&path)?));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Hash of a tree: its sorted `path\0hash\n` lines
+fn tree_hash(files: &BTreeMap<String, String>) -> String {
+    let mut hasher = Sha256::new();
+    for (path, hash) in files {
+        hasher.update(path.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(hash.as_bytes());
+        hasher.update(b"\n");
+    }
+    hex(&hasher.finalize())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// `./src/client/` → `src/client`
+fn normalize_rel(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = path.trim_start_matches("./").trim_end_matches('/');
+    path.to_string()
+}
+
+/// Files added, removed or modified relative to the record, sorted
+fn drifted_files(record: &HashRecord, current: &BTreeMap<String, String>) -> Vec<String> {
+    let mut drifted = Vec::new();
+    for (path, hash) in current {
+        match record.files.get(path) {
+            None => drifted.push(format!("{} (added)", path)),
+            Some(old) if old != hash => drifted.push(path.clone()),
+            Some(_) => {}
+        }
+    }
+    for path in record.files.keys() {
+        if !current.contains_key(path) {
+            drifted.push(format!("{} (removed)", path));
+        }
+    }
+    drifted.sort();
+    drifted
+}
+
+fn list_files(files: &[String]) -> String {
+    let mut listed = files
+        .iter()
+        .take(MAX_LISTED_FILES)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if files.len() > MAX_LISTED_FILES {
+        listed.push_str(&format!(", and {} more", files.len() - MAX_LISTED_FILES));
+    }
+    listed
+}
+
+// ── Analyzer ──────────────────────────────────────────────────────────────────
+
+/// Checks `[[analyzers.generated]]` entries for spec / generated-code drift
+pub struct GeneratedCodeAnalyzer {
+    entries: Vec<GeneratedCodeConfig>,
+}
+
+impl GeneratedCodeAnalyzer {
+    pub fn from_config(config: &RevetConfig) -> Self {
+        Self {
+            entries: config.analyzers.generated.clone(),
+        }
+    }
+
+    fn check_entry(
+        &self,
+        entry: &GeneratedCodeConfig,
+        repo_root: &Path,
+    ) -> Result<Option<Finding>> {
+        let spec = normalize_rel(&entry.spec);
+        let output_dir = normalize_rel(&entry.output_dir);
+        let spec_path = repo_root.join(&spec);
+        let hash_path = repo_root.join(&entry.hash_file);
+
+        if !spec_path.is_file() {
+            return Ok(Some(make_finding(
+                Severity::Warning,
+                format!(
+                    "Spec `{}` for generated code in `{}` not found",
+                    spec, output_dir
+                ),
+                spec_path,
+                1,
+                Some("Fix the `spec` path in `[[analyzers.generated]]`".to_string()),
+                None,
+            )));
+        }
+        if !repo_root.join(&output_dir).is_dir() {
+            return Ok(Some(make_finding(
+                Severity::Warning,
+                format!(
+                    "Generated code directory `{}` for `{}` not found",
+                    output_dir, spec
+                ),
+                spec_path,
+                1,
+                Some(self.regenerate_hint(entry, &output_dir)),
+                self.fix_kind(entry, repo_root),
+            )));
+        }
+
+        let mut hashes = HashFile::load(&hash_path)?;
+        let current = Snapshot::take(repo_root, &spec, &output_dir, &hash_path)?;
+        let Some(record) = hashes.find(&spec, &output_dir).cloned() else {
+            // First run for this pair: record it
+            hashes.upsert(current.into_record(&spec, &output_dir));
+            hashes.save(&hash_path)?;
+            return Ok(None);
+        };
+
+        let spec_changed = record.spec_hash != current.spec_hash;
+        let tree_changed = record.tree_hash != current.tree_hash;
+
+        let message = match (spec_changed, tree_changed) {
+            (false, false) => return Ok(None),
+            (true, true) => {
+                // Regenerated alongside the spec change
+                hashes.upsert(current.into_record(&spec, &output_dir));
+                hashes.save(&hash_path)?;
+                return Ok(None);
+            }
+            (true, false) => format!(
+                "Generated code in `{}` is stale — `{}` changed but the generated files did not",
+                output_dir, spec
+            ),
+            (false, true) => format!(
+                "Generated code in `{}` was edited by hand — `{}` is unchanged (drifted: {})",
+                output_dir,
+                spec,
+                list_files(&drifted_files(&record, &current.files))
+            ),
+        };
+
+        Ok(Some(make_finding(
+            Severity::Warning,
+            message,
+            spec_path,
+            1,
+            Some(self.regenerate_hint(entry, &output_dir)),
+            self.fix_kind(entry, repo_root),
+        )))
+    }
+
+    fn regenerate_hint(&self, entry: &GeneratedCodeConfig, output_dir: &str) -> String {
+        match &entry.command {
+            Some(command) => format!(
+                "Regenerate with `{}` and commit the result; make lasting changes in the spec or generator, not in `{}`",
+                command, output_dir
+            ),
+            None => format!(
+                "Regenerate `{}` from the spec and commit the result",
+                output_dir
+            ),
+        }
+    }
+
+    fn fix_kind(&self, entry: &GeneratedCodeConfig, repo_root: &Path) -> Option<FixKind> {
+        match &entry.command {
+            Some(command) if entry.allow_regenerate => Some(FixKind::Regenerate {
+                command: command.clone(),
+                repo_root: repo_root.to_path_buf(),
+                spec: entry.spec.clone(),
+                output_dir: entry.output_dir.clone(),
+                hash_file: entry.hash_file.clone(),
+            }),
+            _ => Some(FixKind::Suggestion),
+        }
+    }
+}
+
+impl Analyzer for GeneratedCodeAnalyzer {
+    fn name(&self) -> &str {
+        "Generated Code"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "GEN"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        !config.analyzers.generated.is_empty()
+    }
+
+    fn analyze_files(&self, _files: &[PathBuf], repo_root: &Path) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for entry in &self.entries {
+            match self.check_entry(entry, repo_root) {
+                Ok(finding) => findings.extend(finding),
+                Err(e) => eprintln!(
+                    "  warn: generated code check for {:?} failed: {:#}",
+                    entry.output_dir, e
+                ),
+            }
+        }
+        findings
+    }
+}
+
+// ── Regeneration (`--fix`) ────────────────────────────────────────────────────
+
+/// Run `command` from `repo_root`, verify the regenerated tree, and update the
+/// hash record. The files the command changed and the hash file are recorded
+/// in `journal` for the fix of `finding_id`.
+///
+/// Verification depends on what drifted: if the spec is unchanged since the
+/// last record (hand edits), the regenerated tree must match the recorded
+/// tree hash exactly; if the spec changed, the tree must have changed too.
+pub fn regenerate(
+    command: &str,
+    repo_root: &Path,
+    spec: &str,
+    output_dir: &str,
+    hash_file: &str,
+    finding_id: &str,
+    journal: &mut MutationJournal,
+) -> Result<()> {
+    let spec = normalize_rel(spec);
+    let output_dir = normalize_rel(output_dir);
+    let hash_path = repo_root.join(hash_file);
+
+    let mut hashes = HashFile::load(&hash_path)?;
+    let before = hashes.find(&spec, &output_dir).cloned();
+    let out_path = repo_root.join(&output_dir);
+    let contents_before = hash_tree_with(&out_path, &hash_path, audit::content_hash)?;
+
+    let output = shell(command)
+        .current_dir(repo_root)
+        .output()
+        .with_context(|| format!("Failed to run `{}`", command))?;
+    if !output.status.success() {
+        bail!(
+            "`{}` exited with {}: {}",
+            command,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let contents_after = hash_tree_with(&out_path, &hash_path, audit::content_hash)?;
+    let changed: BTreeSet<&String> = contents_before
+        .keys()
+        .chain(contents_after.keys())
+        .collect();
+    for rel in changed {
+        journal.record_hashes(
+            &out_path.join(rel),
+            contents_before.get(rel).cloned(),
+            contents_after.get(rel).cloned(),
+            &[finding_id.to_string()],
+        );
+    }
+
+    let after = Snapshot::take(repo_root, &spec, &output_dir, &hash_path)?;
+    if let Some(record) = &before {
+        if record.spec_hash == after.spec_hash && record.tree_hash != after.tree_hash {
+            bail!(
+                "`{}` did not reproduce the recorded `{}` (still differs: {})",
+                command,
+                output_dir,
+                list_files(&drifted_files(record, &after.files))
+            );
+        }
+        if record.spec_hash != after.spec_hash && record.tree_hash == after.tree_hash {
+            bail!(
+                "`{}` left `{}` unchanged although `{}` changed",
+                command,
+                output_dir,
+                spec
+            );
+        }
+    }
+
+    hashes.upsert(after.into_record(&spec, &output_dir));
+    if let Some(parent) = hash_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    journal.write(&hash_path, &hashes.render()?, &[finding_id.to_string()])
+}
+
+fn shell(command: &str) -> std::process::Command {
+    if cfg!(windows) {
+        let mut cmd = std::process::Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = std::process::Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
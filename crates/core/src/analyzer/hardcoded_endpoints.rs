@@ -104,11 +104,13 @@ impl HardcodedEndpointsAnalyzer {
     }
 
     fn scan_file(path: &Path) -> Vec<Finding> {
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return Vec::new(),
-        };
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::scan_source(path, &content),
+            Err(_) => Vec::new(),
+        }
+    }
 
+    fn scan_source(path: &Path, content: &str) -> Vec<Finding> {
         let all_patterns = patterns();
         let mut findings = Vec::new();
 
@@ -152,6 +154,18 @@ impl Analyzer for HardcodedEndpointsAnalyzer {
         config.modules.hardcoded_endpoints
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("hardcoded_endpoints")
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Self::should_scan(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
     fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
         let mut findings = Vec::new();
         for file in files {
@@ -162,4 +176,11 @@ impl Analyzer for HardcodedEndpointsAnalyzer {
         }
         findings
     }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        if !Self::should_scan(path) {
+            return Vec::new();
+        }
+        Self::scan_source(path, content)
+    }
 }
@@ -0,0 +1,145 @@
+//! Hotspot analyzer (`HOT`) — files that change often *and* are complex
+//!
+//! Combines each file's change frequency over `[analyzers.hotspots] window`
+//! (see [`Churn`]) with its aggregate complexity, the summed cyclomatic
+//! complexity of its functions (see [`file_complexity`]). Files in the top
+//! `percentile` of both dimensions are reported as warnings, and the whole
+//! ranking by `changes × complexity` is kept for `revet stats`, so teams get
+//! a prioritized refactoring list instead of one finding per complex function.
+
+use super::catalog_finding;
+use super::complexity::file_complexity;
+use crate::churn::{Churn, Window};
+use crate::config::RevetConfig;
+use crate::finding::{Finding, Severity};
+use crate::graph::CodeGraph;
+use crate::msg;
+use crate::storage::StoragePaths;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::path::Path;
+
+/// `[modules]` name of the analyzer
+pub const MODULE: &str = "hotspots";
+
+/// One file in the hotspot ranking
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hotspot {
+    /// Path relative to the repository root, `/`-separated
+    pub file: String,
+    /// Commits touching the file inside the window
+    pub changes: usize,
+    /// Summed cyclomatic complexity of the file's functions
+    pub complexity: usize,
+    /// In the top percentile of both dimensions (reported as a finding)
+    pub flagged: bool,
+}
+
+impl Hotspot {
+    /// Ranking score: `changes × complexity`
+    pub fn score(&self) -> usize {
+        self.changes * self.complexity
+    }
+}
+
+/// Result of one hotspot pass
+#[derive(Debug, Clone)]
+pub struct HotspotReport {
+    /// History the change counts come from
+    pub churn: Churn,
+    /// Files with at least one change, highest score first
+    pub ranking: Vec<Hotspot>,
+}
+
+impl HotspotReport {
+    /// Rank the files of `graph` by churn and complexity. Fails when
+    /// `repo_root` has no git history or the window is invalid.
+    pub fn build(graph: &CodeGraph, repo_root: &Path, config: &RevetConfig) -> Result<Self> {
+        let settings = &config.analyzers.hotspots;
+        let window = Window::parse(&settings.window)
+            .ok_or_else(|| anyhow!("invalid hotspot window {:?}", settings.window))?;
+        let paths = StoragePaths::resolve(repo_root, config);
+        let churn = Churn::load_or_compute(repo_root, &paths, window)?;
+
+        let files: Vec<(String, usize, usize)> = file_complexity(graph, config)
+            .into_iter()
+            .map(|(path, complexity)| {
+                let rel = path.strip_prefix(repo_root).unwrap_or(&path);
+                let rel = rel.to_string_lossy().replace('\\', "/");
+                let changes = churn.changes(Path::new(&rel));
+                (rel, changes, complexity)
+            })
+            .collect();
+        let ranking = rank(files, settings.percentile, settings.min_changes);
+        Ok(Self { churn, ranking })
+    }
+
+    /// A warning for every flagged file
+    pub fn findings(&self, repo_root: &Path) -> Vec<Finding> {
+        self.ranking
+            .iter()
+            .filter(|h| h.flagged)
+            .map(|h| {
+                catalog_finding(
+                    Severity::Warning,
+                    msg!(
+                        "hotspots-file",
+                        changes = h.changes,
+                        days = self.churn.window_days,
+                        complexity = h.complexity
+                    ),
+                    repo_root.join(&h.file),
+                    1,
+                    Some(msg!("hotspots-file.suggestion")),
+                    None,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Rank `(file, changes, complexity)` triples and flag the files at or above
+/// the `percentile` of both dimensions with at least `min_changes` changes.
+/// Files that never changed in the window are left out of the ranking.
+pub fn rank(
+    files: Vec<(String, usize, usize)>,
+    percentile: u8,
+    min_changes: usize,
+) -> Vec<Hotspot> {
+    let churn_floor = top_percentile(files.iter().map(|f| f.1), percentile);
+    let complexity_floor = top_percentile(files.iter().map(|f| f.2), percentile);
+
+    let mut ranking: Vec<Hotspot> = files
+        .into_iter()
+        .filter(|(_, changes, _)| *changes > 0)
+        .map(|(file, changes, complexity)| Hotspot {
+            flagged: changes >= churn_floor.max(min_changes).max(1)
+                && complexity >= complexity_floor.max(1),
+            file,
+            changes,
+            complexity,
+        })
+        .collect();
+    ranking.sort_by(|a, b| {
+        (Reverse(a.score()), Reverse(a.changes), &a.file).cmp(&(
+            Reverse(b.score()),
+            Reverse(b.changes),
+            &b.file,
+        ))
+    });
+    ranking
+}
+
+/// Smallest value still in the top `100 - percentile` % of `values` (at
+/// least the single highest value)
+fn top_percentile(values: impl Iterator<Item = usize>, percentile: u8) -> usize {
+    let mut values: Vec<usize> = values.collect();
+    if values.is_empty() {
+        return usize::MAX;
+    }
+    values.sort_unstable_by(|a, b| b.cmp(a));
+    let share = 100 - usize::from(percentile.min(99));
+    let keep = (values.len() * share).div_ceil(100).max(1);
+    values[keep - 1]
+}
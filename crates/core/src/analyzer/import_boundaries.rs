@@ -0,0 +1,133 @@
+//! Import boundaries analyzer — flags imports that cross from production code
+//! into code that does not ship with it.
+//!
+//! Reads the [`ImportKind`] the resolver puts on file → import edges:
+//!
+//! - `test_import`: a production file imports a test file
+//! - `dev_dependency`: a file reachable from production code through runtime
+//!   imports uses a package its manifest declares only for development
+//!
+//! Type-only imports are erased at runtime and never cross a boundary.
+
+use crate::analyzer::{catalog_finding, GraphAnalyzer};
+use crate::code_kind::CodeKind;
+use crate::config::RevetConfig;
+use crate::finding::{Finding, Severity};
+use crate::graph::{CodeGraph, EdgeKind, ImportKind, NodeData, NodeId, NodeKind};
+use crate::msg;
+use std::collections::HashSet;
+
+pub struct ImportBoundaryAnalyzer;
+
+impl Default for ImportBoundaryAnalyzer {
+    fn default() -> Self {
+        Self
+    }
+}
+
+impl ImportBoundaryAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn code_kind(graph: &CodeGraph, file: NodeId) -> Option<CodeKind> {
+    match graph.node(file)?.data() {
+        NodeData::File { code_kind, .. } => Some(*code_kind),
+        _ => None,
+    }
+}
+
+/// Production files and every file they import at runtime, transitively
+fn reachable_from_production(graph: &CodeGraph) -> HashSet<NodeId> {
+    let mut stack: Vec<NodeId> = graph
+        .nodes()
+        .filter(|(id, _)| code_kind(graph, *id) == Some(CodeKind::Production))
+        .map(|(id, _)| id)
+        .collect();
+    let mut reached: HashSet<NodeId> = stack.iter().copied().collect();
+    while let Some(file) = stack.pop() {
+        for (target, edge) in graph.edges_from(file) {
+            if matches!(edge.kind(), EdgeKind::Imports)
+                && edge.import_kind() == ImportKind::Runtime
+                && code_kind(graph, target).is_some()
+                && reached.insert(target)
+            {
+                stack.push(target);
+            }
+        }
+    }
+    reached
+}
+
+impl GraphAnalyzer for ImportBoundaryAnalyzer {
+    fn name(&self) -> &str {
+        "Import Boundaries"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "BOUNDARY"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.dependency
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some("dependency")
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        &["test_import", "dev_dependency"]
+    }
+
+    fn analyze_graph(&self, graph: &CodeGraph, _config: &RevetConfig) -> Vec<Finding> {
+        let reachable = reachable_from_production(graph);
+        let mut findings = Vec::new();
+
+        for &file in &reachable {
+            let production = code_kind(graph, file) == Some(CodeKind::Production);
+            for (target, edge) in graph.edges_from(file) {
+                if !matches!(edge.kind(), EdgeKind::Imports) {
+                    continue;
+                }
+                let Some(import) = graph.node(target) else {
+                    continue;
+                };
+                if !matches!(import.kind(), NodeKind::Import) {
+                    continue;
+                }
+                let NodeData::Import { module, .. } = import.data() else {
+                    continue;
+                };
+                let (rule, message, suggestion) = match edge.import_kind() {
+                    ImportKind::Test if production => (
+                        "test_import",
+                        msg!("boundary-test-import", module = module.as_str()),
+                        msg!("boundary-test-import.suggestion"),
+                    ),
+                    ImportKind::Dev => (
+                        "dev_dependency",
+                        msg!("boundary-dev-dependency", module = module.as_str()),
+                        msg!("boundary-dev-dependency.suggestion"),
+                    ),
+                    _ => continue,
+                };
+                findings.push(Finding {
+                    rule: Some(rule.to_string()),
+                    ..catalog_finding(
+                        Severity::Warning,
+                        message,
+                        import.file_path().clone(),
+                        import.line(),
+                        Some(suggestion),
+                        None,
+                    )
+                });
+            }
+        }
+
+        findings.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        findings
+    }
+}
@@ -56,6 +56,8 @@ struct InfraPattern {
     target_filenames: &'static [&'static str],
     suggestion: &'static str,
     fix_kind: FixKind,
+    /// Also checked, position-aware, by the `K8S` analyzer
+    kubernetes: bool,
 }
 
 /// Returns all infrastructure patterns in priority order (Error → Warning → Info)
@@ -77,6 +79,7 @@ fn patterns() -> &'static [InfraPattern] {
                     find: r#"public-read(?:-write)?"#.to_string(),
                     replace: "private".to_string(),
                 },
+                kubernetes: false,
             },
             // Pattern 2: Open security group (0.0.0.0/0)
             InfraPattern {
@@ -88,6 +91,7 @@ fn patterns() -> &'static [InfraPattern] {
                 target_filenames: &[],
                 suggestion: "Restrict CIDR block to specific IP ranges instead of 0.0.0.0/0",
                 fix_kind: FixKind::Suggestion,
+                kubernetes: false,
             },
             // Pattern 3: Hardcoded provider credentials
             InfraPattern {
@@ -100,6 +104,7 @@ fn patterns() -> &'static [InfraPattern] {
                 target_filenames: &[],
                 suggestion: "Use Terraform variables or environment variables for credentials",
                 fix_kind: FixKind::Suggestion,
+                kubernetes: false,
             },
             // ── Warning: likely problematic ──────────────────────────────
             // Pattern 4: Wildcard IAM actions
@@ -113,6 +118,7 @@ fn patterns() -> &'static [InfraPattern] {
                 target_filenames: &[],
                 suggestion: "Specify explicit IAM actions instead of using wildcard \"*\"",
                 fix_kind: FixKind::Suggestion,
+                kubernetes: false,
             },
             // Pattern 5: Docker FROM :latest or no tag
             InfraPattern {
@@ -124,6 +130,7 @@ fn patterns() -> &'static [InfraPattern] {
                 target_filenames: &["Dockerfile"],
                 suggestion: "Pin Docker image to a specific version tag for reproducible builds",
                 fix_kind: FixKind::Suggestion,
+                kubernetes: false,
             },
             // Pattern 6: Privileged container
             InfraPattern {
@@ -138,6 +145,7 @@ fn patterns() -> &'static [InfraPattern] {
                     find: r"privileged:\s*true".to_string(),
                     replace: "privileged: false".to_string(),
                 },
+                kubernetes: true,
             },
             // Pattern 7: HostPath volume mount
             InfraPattern {
@@ -149,6 +157,7 @@ fn patterns() -> &'static [InfraPattern] {
                 target_filenames: &[],
                 suggestion: "Use emptyDir, configMap, or PVC instead of hostPath volumes",
                 fix_kind: FixKind::Suggestion,
+                kubernetes: false,
             },
             // Pattern 8: K8s image :latest tag in pod spec
             InfraPattern {
@@ -160,6 +169,7 @@ fn patterns() -> &'static [InfraPattern] {
                 target_filenames: &[],
                 suggestion: "Pin the image to a specific digest or version tag (e.g. nginx:1.25.3)",
                 fix_kind: FixKind::Suggestion,
+                kubernetes: true,
             },
             // Pattern 9: Docker ADD instruction (implicit tar extraction / remote URL risk)
             InfraPattern {
@@ -171,6 +181,7 @@ fn patterns() -> &'static [InfraPattern] {
                 target_filenames: &["Dockerfile"],
                 suggestion: "Use COPY instead of ADD; ADD silently extracts tarballs and can fetch remote URLs",
                 fix_kind: FixKind::Suggestion,
+                kubernetes: false,
             },
             // Pattern 10: Docker USER root
             InfraPattern {
@@ -182,6 +193,7 @@ fn patterns() -> &'static [InfraPattern] {
                 target_filenames: &["Dockerfile"],
                 suggestion: "Create and switch to a non-root user: RUN useradd -m appuser && USER appuser",
                 fix_kind: FixKind::Suggestion,
+                kubernetes: false,
             },
             // Pattern 11: Docker COPY . . (copies .env, secrets, entire repo into image)
             InfraPattern {
@@ -193,6 +205,7 @@ fn patterns() -> &'static [InfraPattern] {
                 target_filenames: &["Dockerfile"],
                 suggestion: "Use a .dockerignore file to exclude .env, secrets, and other sensitive files",
                 fix_kind: FixKind::Suggestion,
+                kubernetes: false,
             },
             // ── Info: best practice ──────────────────────────────────────
             // Pattern 12: HTTP backend/source URL
@@ -208,6 +221,7 @@ fn patterns() -> &'static [InfraPattern] {
                     find: r"http://".to_string(),
                     replace: "https://".to_string(),
                 },
+                kubernetes: false,
             },
         ]
     })
@@ -228,12 +242,21 @@ const BINARY_EXTENSIONS: &[&str] = &[
 ];
 
 /// Analyzer that detects infrastructure misconfigurations
-pub struct InfraAnalyzer;
+pub struct InfraAnalyzer {
+    /// Run the Kubernetes manifest checks the `K8S` analyzer also covers
+    kubernetes: bool,
+}
 
 impl InfraAnalyzer {
     /// Create a new infrastructure analyzer
     pub fn new() -> Self {
-        Self
+        Self { kubernetes: true }
+    }
+
+    /// Create an infrastructure analyzer that leaves privileged containers,
+    /// `:latest` images, probes and resource limits to the `K8S` analyzer
+    pub fn without_kubernetes() -> Self {
+        Self { kubernetes: false }
     }
 
     /// Check if a file should be scanned (must match infra file types, not binary)
@@ -285,18 +308,21 @@ impl InfraAnalyzer {
     }
 
     /// Scan a single file for infrastructure patterns
-    fn scan_file(path: &Path) -> Vec<Finding> {
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return Vec::new(),
-        };
+    fn scan_file(&self, path: &Path) -> Vec<Finding> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => self.scan_source(path, &content),
+            Err(_) => Vec::new(),
+        }
+    }
 
+    fn scan_source(&self, path: &Path, content: &str) -> Vec<Finding> {
         let all_patterns = patterns();
         let mut findings = Vec::new();
 
         // Filter patterns applicable to this file
         let applicable: Vec<&InfraPattern> = all_patterns
             .iter()
+            .filter(|p| self.kubernetes || !p.kubernetes)
             .filter(|p| Self::pattern_matches_file(p, path))
             .collect();
 
@@ -339,7 +365,7 @@ impl InfraAnalyzer {
             path.extension().and_then(|e| e.to_str()),
             Some("yaml") | Some("yml")
         );
-        if is_k8s_yaml {
+        if is_k8s_yaml && self.kubernetes {
             let has_containers = lines.iter().any(|l| re_k8s_containers().is_match(l));
             if has_containers {
                 if !lines.iter().any(|l| re_k8s_readiness().is_match(l)) {
@@ -455,6 +481,18 @@ impl Analyzer for InfraAnalyzer {
         config.modules.infra
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("infra")
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Self::should_scan(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
     fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
         let mut findings = Vec::new();
 
@@ -462,12 +500,19 @@ impl Analyzer for InfraAnalyzer {
             if !Self::should_scan(file) {
                 continue;
             }
-            findings.extend(Self::scan_file(file));
+            findings.extend(self.scan_file(file));
         }
 
         findings
     }
 
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        if !Self::should_scan(path) {
+            return Vec::new();
+        }
+        self.scan_source(path, content)
+    }
+
     fn extra_extensions(&self) -> &[&str] {
         &[".tf", ".tfvars", ".yaml", ".yml", ".json"]
     }
@@ -0,0 +1,506 @@
+//! Kubernetes analyzer — detects misconfigured workloads in manifests and Helm charts
+//!
+//! Every YAML document is read with its positions kept (see [`yaml`]), so each
+//! finding lands on the key at fault. Workloads (`Pod`, `Deployment`,
+//! `StatefulSet`, `DaemonSet`, `ReplicaSet`, `Job`, `CronJob`) are checked for
+//! containers without `resources.limits`, privileged containers, pods sharing
+//! host namespaces, images with neither a version tag nor a digest, secret-looking
+//! env vars set with a literal `value`, and long-running containers without
+//! liveness/readiness probes.
+//!
+//! Helm templates are read render-free: `{{ ... }}` expressions are opaque, and
+//! a templated value is never flagged since what it renders to is unknown. A
+//! chart's `values.yaml` (next to `Chart.yaml`) is checked for insecure
+//! defaults; those findings have Medium confidence, as installs may override
+//! them. Each rule can be switched off under `[kubernetes]`.
+
+pub mod yaml;
+
+use crate::analyzer::{make_finding, Analyzer};
+use crate::config::{KubernetesConfig, RevetConfig};
+use crate::finding::{Confidence, Finding, FixKind, Severity};
+use std::path::{Path, PathBuf};
+use yaml::{Node, Value};
+
+/// Workload kinds and the path from the document root to their pod spec
+const WORKLOADS: &[(&str, &[&str])] = &[
+    ("Pod", &["spec"]),
+    ("Deployment", &["spec", "template", "spec"]),
+    ("StatefulSet", &["spec", "template", "spec"]),
+    ("DaemonSet", &["spec", "template", "spec"]),
+    ("ReplicaSet", &["spec", "template", "spec"]),
+    ("ReplicationController", &["spec", "template", "spec"]),
+    ("Job", &["spec", "template", "spec"]),
+    (
+        "CronJob",
+        &["spec", "jobTemplate", "spec", "template", "spec"],
+    ),
+];
+
+/// Kinds whose containers run indefinitely and so need probes
+const LONG_RUNNING: &[&str] = &["Deployment", "StatefulSet", "DaemonSet"];
+
+/// Pod spec fields that share a host namespace
+const HOST_NAMESPACES: &[(&str, &str)] = &[
+    ("hostNetwork", "network"),
+    ("hostPID", "process"),
+    ("hostIPC", "IPC"),
+];
+
+/// Env var name fragments that suggest a credential
+const SECRET_NAME_PARTS: &[&str] = &[
+    "PASSWORD",
+    "PASSWD",
+    "SECRET",
+    "TOKEN",
+    "API_KEY",
+    "APIKEY",
+    "ACCESS_KEY",
+    "PRIVATE_KEY",
+    "CREDENTIAL",
+];
+
+/// Where a document came from, which decides the checks it gets
+#[derive(Clone, Copy, PartialEq)]
+enum Source {
+    Manifest,
+    HelmValues,
+}
+
+/// Analyzer that detects Kubernetes and Helm misconfigurations
+pub struct KubernetesAnalyzer {
+    rules: KubernetesConfig,
+}
+
+impl KubernetesAnalyzer {
+    /// Create a new Kubernetes analyzer with every rule on
+    pub fn new() -> Self {
+        Self::with_rules(&KubernetesConfig::default())
+    }
+
+    /// Create a Kubernetes analyzer honouring `[kubernetes]` rule switches
+    pub fn with_rules(rules: &KubernetesConfig) -> Self {
+        Self {
+            rules: rules.clone(),
+        }
+    }
+
+    fn should_scan(path: &Path) -> bool {
+        matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("yaml" | "yml")
+        )
+    }
+
+    /// `values.yaml` / `values.yml` sitting next to a `Chart.yaml`
+    fn is_helm_values(path: &Path) -> bool {
+        let is_values = matches!(
+            path.file_name().and_then(|n| n.to_str()),
+            Some("values.yaml" | "values.yml")
+        );
+        is_values && path.with_file_name("Chart.yaml").is_file()
+    }
+
+    fn scan_source(&self, path: &Path, content: &str) -> Vec<Finding> {
+        let source = if Self::is_helm_values(path) {
+            Source::HelmValues
+        } else {
+            Source::Manifest
+        };
+        if source == Source::HelmValues && !self.rules.helm_values {
+            return Vec::new();
+        }
+
+        let mut scan = Scan {
+            rules: &self.rules,
+            path,
+            source,
+            findings: Vec::new(),
+        };
+        for document in yaml::parse_documents(content) {
+            match source {
+                Source::Manifest => scan.manifest(&document),
+                Source::HelmValues => scan.values(&document),
+            }
+        }
+        scan.findings
+    }
+}
+
+impl Default for KubernetesAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Findings collected from one file
+struct Scan<'a> {
+    rules: &'a KubernetesConfig,
+    path: &'a Path,
+    source: Source,
+    findings: Vec<Finding>,
+}
+
+impl Scan<'_> {
+    fn push(
+        &mut self,
+        rule: &'static str,
+        severity: Severity,
+        message: String,
+        line: usize,
+        suggestion: &str,
+        fix_kind: FixKind,
+    ) {
+        let mut finding = make_finding(
+            severity,
+            format!("Kubernetes: {}", message),
+            self.path.to_path_buf(),
+            line,
+            Some(suggestion.to_string()),
+            Some(fix_kind),
+        );
+        finding.rule = Some(rule.to_string());
+        if self.source == Source::HelmValues {
+            finding.confidence = Confidence::Medium;
+        }
+        self.findings.push(finding);
+    }
+
+    // ── Manifests ────────────────────────────────────────────────
+
+    fn manifest(&mut self, document: &Node) {
+        let Some(kind) = document.get("kind").and_then(Node::as_str) else {
+            return;
+        };
+        let Some((_, spec_path)) = WORKLOADS.iter().find(|(k, _)| *k == kind) else {
+            return;
+        };
+        let Some(pod) = document.path(spec_path) else {
+            return;
+        };
+        let workload = document
+            .path(&["metadata", "name"])
+            .and_then(Node::as_str)
+            .unwrap_or(kind);
+
+        if self.rules.host_namespaces {
+            self.host_namespaces(pod, "pod shares");
+        }
+
+        let long_running = LONG_RUNNING.contains(&kind);
+        for container in pod.get("containers").map_or(&[][..], Node::items) {
+            self.container(container);
+            if long_running && self.rules.probes {
+                self.probes(container, kind, workload);
+            }
+        }
+        for container in pod.get("initContainers").map_or(&[][..], Node::items) {
+            self.container(container);
+        }
+    }
+
+    fn container(&mut self, container: &Node) {
+        let name = container_name(container);
+
+        if self.rules.image_pinning {
+            if let Some(image) = container.entry("image") {
+                self.image(&image.value, &format!("container `{}`", name), image.line);
+            }
+        }
+
+        if self.rules.privileged {
+            if let Some(privileged) = container
+                .get("securityContext")
+                .and_then(|sc| sc.entry("privileged"))
+                .filter(|e| e.value.is_true())
+            {
+                self.privileged(
+                    format!("container `{}` runs privileged", name),
+                    privileged.line,
+                );
+            }
+        }
+
+        if self.rules.resource_limits {
+            self.resource_limits(container, &name);
+        }
+
+        if self.rules.env_secrets {
+            for var in container.get("env").map_or(&[][..], Node::items) {
+                self.env_secret(var, &name);
+            }
+        }
+    }
+
+    fn resource_limits(&mut self, container: &Node, name: &str) {
+        let message = format!(
+            "container `{}` has no resources.limits (can starve other pods on the node)",
+            name
+        );
+        let Some(resources) = container.entry("resources") else {
+            self.push(
+                "resource_limits",
+                Severity::Warning,
+                message,
+                container.line,
+                "Set resources.limits (and requests) for cpu and memory",
+                FixKind::Suggestion,
+            );
+            return;
+        };
+        if resources.value.is_templated() {
+            return;
+        }
+        match resources.value.get("limits") {
+            Some(limits) if limits.is_templated() || !limits.entries().is_empty() => {}
+            _ => self.push(
+                "resource_limits",
+                Severity::Warning,
+                message,
+                resources.line,
+                "Set resources.limits (and requests) for cpu and memory",
+                FixKind::Suggestion,
+            ),
+        }
+    }
+
+    fn env_secret(&mut self, var: &Node, container: &str) {
+        let Some(name) = var.get("name").and_then(Node::as_str) else {
+            return;
+        };
+        let Some(value) = var.entry("value") else {
+            return;
+        };
+        let literal = value.value.as_str().is_some_and(|v| !v.is_empty());
+        if !literal || value.value.is_templated() || !is_secret_name(name) {
+            return;
+        }
+        self.push(
+            "env_secrets",
+            Severity::Error,
+            format!(
+                "secret-looking env var `{}` in container `{}` is set with a literal value",
+                name, container
+            ),
+            value.line,
+            "Store the value in a Secret and reference it with valueFrom.secretKeyRef",
+            FixKind::Suggestion,
+        );
+    }
+
+    fn probes(&mut self, container: &Node, kind: &str, workload: &str) {
+        let missing: Vec<&str> = ["readinessProbe", "livenessProbe"]
+            .into_iter()
+            .filter(|probe| container.get(probe).is_none())
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+        self.push(
+            "probes",
+            Severity::Warning,
+            format!(
+                "container `{}` in {} `{}` has no {}",
+                container_name(container),
+                kind,
+                workload,
+                missing.join(" or ")
+            ),
+            container.line,
+            "Add a readinessProbe so traffic waits for the pod, and a livenessProbe so stuck pods restart",
+            FixKind::Suggestion,
+        );
+    }
+
+    // ── Shared checks ────────────────────────────────────────────
+
+    fn host_namespaces(&mut self, pod: &Node, subject: &str) {
+        for (field, namespace) in HOST_NAMESPACES {
+            if let Some(entry) = pod.entry(field).filter(|e| e.value.is_true()) {
+                self.push(
+                    "host_namespaces",
+                    Severity::Error,
+                    format!(
+                        "{} the host {} namespace (`{}: true`)",
+                        subject, namespace, field
+                    ),
+                    entry.line,
+                    "Remove the host namespace setting; expose ports through a Service instead of hostNetwork",
+                    FixKind::Suggestion,
+                );
+            }
+        }
+    }
+
+    fn privileged(&mut self, message: String, line: usize) {
+        self.push(
+            "privileged",
+            Severity::Error,
+            format!("{} (root access to the host)", message),
+            line,
+            "Set privileged: false and grant only the capabilities the container needs",
+            FixKind::ReplacePattern {
+                find: r"privileged:\s*true".to_string(),
+                replace: "privileged: false".to_string(),
+            },
+        );
+    }
+
+    fn image(&mut self, image: &Node, subject: &str, line: usize) {
+        let Some(reference) = image.as_str().filter(|r| !r.is_empty()) else {
+            return;
+        };
+        if image.is_templated() || reference.contains('@') {
+            return;
+        }
+        let problem = match image_tag(reference) {
+            Some("latest") => "uses the mutable `latest` tag",
+            Some(_) => return,
+            None => "has no tag or digest",
+        };
+        self.push(
+            "image_pinning",
+            Severity::Warning,
+            format!("{} image `{}` {}", subject, reference, problem),
+            line,
+            "Pin the image to a version tag or digest (e.g. nginx:1.25.3 or nginx@sha256:...)",
+            FixKind::Suggestion,
+        );
+    }
+
+    // ── Helm values ──────────────────────────────────────────────
+
+    /// Walk a chart's `values.yaml` for insecure defaults
+    fn values(&mut self, node: &Node) {
+        match &node.value {
+            Value::Mapping(entries) => {
+                if self.rules.host_namespaces {
+                    self.host_namespaces(node, "default shares");
+                }
+                for entry in entries {
+                    match entry.key.as_str() {
+                        "privileged" if self.rules.privileged && entry.value.is_true() => {
+                            self.privileged(
+                                "default enables privileged containers".to_string(),
+                                entry.line,
+                            );
+                        }
+                        "image" if self.rules.image_pinning => match entry.value.get("tag") {
+                            Some(tag) if tag.as_str() == Some("latest") => self.push(
+                                "image_pinning",
+                                Severity::Warning,
+                                "default image tag is the mutable `latest`".to_string(),
+                                tag.line,
+                                "Default the tag to a version, or leave it empty to use the chart's appVersion",
+                                FixKind::Suggestion,
+                            ),
+                            Some(_) => {}
+                            None => self.image(&entry.value, "default", entry.line),
+                        },
+                        "resources" if self.rules.resource_limits => {
+                            let limits = entry.value.get("limits");
+                            if limits.is_none_or(|l| l.entries().is_empty() && !l.is_templated()) {
+                                self.push(
+                                    "resource_limits",
+                                    Severity::Warning,
+                                    "default `resources` sets no limits".to_string(),
+                                    entry.line,
+                                    "Give resources.limits a default for cpu and memory",
+                                    FixKind::Suggestion,
+                                );
+                            }
+                        }
+                        _ => {}
+                    }
+                    self.values(&entry.value);
+                }
+            }
+            Value::Sequence(items) => {
+                for item in items {
+                    self.values(item);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn container_name(container: &Node) -> String {
+    container
+        .get("name")
+        .and_then(Node::as_str)
+        .unwrap_or("?")
+        .to_string()
+}
+
+/// The tag of an image reference (`registry:5000/app:1.2` → `1.2`)
+fn image_tag(reference: &str) -> Option<&str> {
+    let name = reference.rsplit('/').next().unwrap_or(reference);
+    name.split_once(':').map(|(_, tag)| tag)
+}
+
+fn is_secret_name(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    SECRET_NAME_PARTS.iter().any(|part| upper.contains(part))
+}
+
+impl Analyzer for KubernetesAnalyzer {
+    fn name(&self) -> &str {
+        "Kubernetes"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "K8S"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.kubernetes
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some("kubernetes")
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        &[
+            "resource_limits",
+            "privileged",
+            "host_namespaces",
+            "image_pinning",
+            "env_secrets",
+            "probes",
+        ]
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Self::should_scan(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for file in files {
+            if !Self::should_scan(file) {
+                continue;
+            }
+            if let Ok(content) = std::fs::read_to_string(file) {
+                findings.extend(self.scan_source(file, &content));
+            }
+        }
+        findings
+    }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        if !Self::should_scan(path) {
+            return Vec::new();
+        }
+        self.scan_source(path, content)
+    }
+
+    fn extra_extensions(&self) -> &[&str] {
+        &[".yaml", ".yml"]
+    }
+}
@@ -0,0 +1,638 @@
+//! Position-preserving reader for the YAML used by Kubernetes manifests
+//!
+//! Covers the block subset manifests are written in: multi-document streams,
+//! block mappings and sequences (including sequences at their key's indent),
+//! plain and quoted scalars, block scalars (`|`, `>`), flow collections and
+//! comments. Every node keeps the 1-based line it starts on. Anchors and tags
+//! are skipped, aliases read as plain scalars, complex keys are ignored.
+//!
+//! Helm templates are read render-free. A scalar containing `{{ ... }}` stays
+//! a scalar that reports [`Node::is_templated`]. A line holding nothing but
+//! template actions (`{{- if .Values.x }}`, `{{- toYaml .Values.r | nindent 8 }}`)
+//! is skipped, unless it is the whole body of a `key:` — then the key's value
+//! is [`Value::Templated`].
+
+/// A YAML value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Scalar(String),
+    /// Produced entirely by template actions; unknown until rendered
+    Templated,
+    Sequence(Vec<Node>),
+    Mapping(Vec<Entry>),
+}
+
+/// A value and the line it starts on
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub line: usize,
+    pub value: Value,
+}
+
+/// A mapping entry; `line` is where the key is written
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub key: String,
+    pub line: usize,
+    pub value: Node,
+}
+
+impl Node {
+    /// The entry for `key`, if this is a mapping that has one
+    pub fn entry(&self, key: &str) -> Option<&Entry> {
+        self.entries().iter().find(|e| e.key == key)
+    }
+
+    /// The value for `key`, if this is a mapping that has one
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        self.entry(key).map(|e| &e.value)
+    }
+
+    /// Follow a chain of mapping keys
+    pub fn path(&self, keys: &[&str]) -> Option<&Node> {
+        keys.iter().try_fold(self, |node, key| node.get(key))
+    }
+
+    /// The scalar text, if this is a scalar
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            Value::Scalar(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// A literal `true` (templated values are never true)
+    pub fn is_true(&self) -> bool {
+        matches!(self.as_str(), Some("true" | "True" | "TRUE"))
+    }
+
+    /// Whether the value depends on template rendering
+    pub fn is_templated(&self) -> bool {
+        match &self.value {
+            Value::Templated => true,
+            Value::Scalar(s) => s.contains("{{"),
+            _ => false,
+        }
+    }
+
+    /// Sequence items (empty for anything else)
+    pub fn items(&self) -> &[Node] {
+        match &self.value {
+            Value::Sequence(items) => items,
+            _ => &[],
+        }
+    }
+
+    /// Mapping entries (empty for anything else)
+    pub fn entries(&self) -> &[Entry] {
+        match &self.value {
+            Value::Mapping(entries) => entries,
+            _ => &[],
+        }
+    }
+}
+
+/// Parse every non-empty document in a YAML stream
+pub fn parse_documents(content: &str) -> Vec<Node> {
+    let mut documents = Vec::new();
+    let mut current: Vec<Line> = Vec::new();
+    for (i, raw) in content.lines().enumerate() {
+        if is_document_marker(raw) {
+            documents.extend(parse_document(std::mem::take(&mut current)));
+        } else {
+            current.push(Line::new(i + 1, raw));
+        }
+    }
+    documents.extend(parse_document(current));
+    documents
+}
+
+fn is_document_marker(raw: &str) -> bool {
+    let raw = raw.trim_end();
+    raw == "---" || raw == "..." || raw.starts_with("--- ") || raw.starts_with("---\t")
+}
+
+fn parse_document(lines: Vec<Line>) -> Option<Node> {
+    let mut parser = Parser { lines, pos: 0 };
+    let first = parser.peek()?;
+    let indent = parser.lines[first].indent;
+    let root = parser.parse_block(indent);
+    (root.value != Value::Null).then_some(root)
+}
+
+// ── Lines ────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LineKind {
+    /// Empty or comment only
+    Blank,
+    /// Only template actions
+    Template,
+    Content,
+}
+
+struct Line<'a> {
+    no: usize,
+    indent: usize,
+    raw: &'a str,
+    /// Without indentation or trailing comment
+    text: String,
+    kind: LineKind,
+}
+
+impl<'a> Line<'a> {
+    fn new(no: usize, raw: &'a str) -> Self {
+        let indent = raw.len() - raw.trim_start_matches(' ').len();
+        let text = strip_comment(&raw[indent..]).trim_end().to_string();
+        let kind = if text.trim().is_empty() {
+            LineKind::Blank
+        } else if is_template_only(&text) {
+            LineKind::Template
+        } else {
+            LineKind::Content
+        };
+        Self {
+            no,
+            indent,
+            raw,
+            text,
+            kind,
+        }
+    }
+}
+
+/// Drop a trailing `# comment` outside quotes and template actions
+fn strip_comment(text: &str) -> &str {
+    let bytes = text.as_bytes();
+    let mut quote: Option<u8> = None;
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if let Some(q) = quote {
+            if b == q {
+                quote = None;
+            }
+        } else if bytes[i..].starts_with(b"{{") {
+            depth += 1;
+            i += 2;
+            continue;
+        } else if depth > 0 {
+            if bytes[i..].starts_with(b"}}") {
+                depth -= 1;
+                i += 2;
+                continue;
+            }
+        } else if (b == b'"' || b == b'\'') && starts_token(bytes, i) {
+            quote = Some(b);
+        } else if b == b'#' && (i == 0 || bytes[i - 1] == b' ' || bytes[i - 1] == b'\t') {
+            return &text[..i];
+        }
+        i += 1;
+    }
+    text
+}
+
+/// A quote opens a quoted scalar only at the start of a token
+fn starts_token(bytes: &[u8], i: usize) -> bool {
+    i == 0
+        || matches!(
+            bytes[i - 1],
+            b' ' | b'\t' | b':' | b'[' | b'{' | b',' | b'-'
+        )
+}
+
+fn is_template_only(text: &str) -> bool {
+    let text = text.trim();
+    if !text.starts_with("{{") {
+        return false;
+    }
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        if !rest[..start].trim().is_empty() {
+            return false;
+        }
+        match rest[start..].find("}}") {
+            Some(end) => rest = &rest[start + end + 2..],
+            None => return false,
+        }
+    }
+    rest.trim().is_empty()
+}
+
+fn is_seq_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Split `key: rest` at the first mapping colon
+fn split_key(text: &str) -> Option<(String, &str)> {
+    if text.starts_with('[') || text.starts_with("? ") {
+        return None;
+    }
+    if text.starts_with('{') && !text.starts_with("{{") {
+        return None;
+    }
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    if let Some(&q) = bytes.first().filter(|b| **b == b'"' || **b == b'\'') {
+        let close = text[1..].find(q as char)? + 1;
+        let after = text[close + 1..].trim_start();
+        let rest = after.strip_prefix(':')?;
+        if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+            return None;
+        }
+        return Some((text[1..close].to_string(), rest.trim()));
+    }
+    let mut depth = 0usize;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"{{") {
+            depth += 1;
+            i += 2;
+            continue;
+        }
+        if depth > 0 {
+            if bytes[i..].starts_with(b"}}") {
+                depth -= 1;
+                i += 2;
+            } else {
+                i += 1;
+            }
+            continue;
+        }
+        if bytes[i] == b':' && matches!(bytes.get(i + 1), None | Some(b' ' | b'\t')) {
+            let key = text[..i].trim();
+            return Some((key.to_string(), text[i + 1..].trim()));
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Skip leading `&anchor` and `!tag` properties
+fn strip_properties(mut text: &str) -> &str {
+    while text.starts_with('&') || text.starts_with('!') {
+        text = match text.find([' ', '\t']) {
+            Some(end) => text[end..].trim_start(),
+            None => "",
+        };
+    }
+    text
+}
+
+fn scalar(text: &str) -> Value {
+    let text = text.trim();
+    if let Some(inner) = text.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+        return Value::Scalar(
+            inner
+                .replace("\\\"", "\"")
+                .replace("\\n", "\n")
+                .replace("\\\\", "\\"),
+        );
+    }
+    if let Some(inner) = text.strip_prefix('\'').and_then(|t| t.strip_suffix('\'')) {
+        return Value::Scalar(inner.replace("''", "'"));
+    }
+    match text {
+        "" | "~" | "null" | "Null" | "NULL" => Value::Null,
+        _ => Value::Scalar(text.to_string()),
+    }
+}
+
+// ── Block structure ──────────────────────────────────────────────
+
+struct Parser<'a> {
+    lines: Vec<Line<'a>>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    /// Index of the next content line, without consuming it
+    fn peek(&self) -> Option<usize> {
+        (self.pos..self.lines.len()).find(|&i| self.lines[i].kind == LineKind::Content)
+    }
+
+    /// A node whose first content line sits at exactly `indent`
+    fn parse_block(&mut self, indent: usize) -> Node {
+        let Some(i) = self.peek() else {
+            return Node {
+                line: self.lines.last().map_or(1, |l| l.no),
+                value: Value::Null,
+            };
+        };
+        let text = self.lines[i].text.clone();
+        if is_seq_item(&text) {
+            self.parse_sequence(indent)
+        } else if split_key(&text).is_some() {
+            self.parse_mapping(indent)
+        } else {
+            let no = self.lines[i].no;
+            self.pos = i + 1;
+            self.parse_inline(indent.saturating_sub(1), &text, no)
+        }
+    }
+
+    fn parse_mapping(&mut self, indent: usize) -> Node {
+        let line = self.peek().map_or(1, |i| self.lines[i].no);
+        let mut entries = Vec::new();
+        while let Some(i) = self.peek() {
+            let l = &self.lines[i];
+            if l.indent < indent || (l.indent == indent && is_seq_item(&l.text)) {
+                break;
+            }
+            let no = l.no;
+            let parsed = (l.indent == indent)
+                .then(|| split_key(&l.text))
+                .flatten()
+                .map(|(key, rest)| (key, rest.to_string()));
+            self.pos = i + 1;
+            // Deeper lines nothing claimed (plain scalar continuations) are skipped
+            let Some((key, rest)) = parsed else {
+                continue;
+            };
+            let value = self.parse_inline(indent, &rest, no);
+            entries.push(Entry {
+                key,
+                line: no,
+                value,
+            });
+        }
+        Node {
+            line,
+            value: Value::Mapping(entries),
+        }
+    }
+
+    fn parse_sequence(&mut self, indent: usize) -> Node {
+        let line = self.peek().map_or(1, |i| self.lines[i].no);
+        let mut items = Vec::new();
+        while let Some(i) = self.peek() {
+            let l = &self.lines[i];
+            if l.indent < indent || (l.indent == indent && !is_seq_item(&l.text)) {
+                break;
+            }
+            if l.indent > indent {
+                self.pos = i + 1;
+                continue;
+            }
+            let no = l.no;
+            let rest = l.text[1..].trim_start().to_string();
+            if rest.is_empty() {
+                self.pos = i + 1;
+                items.push(self.parse_nested(indent, false, no));
+                continue;
+            }
+            // Re-read the rest of the line as the item's first line
+            let column = indent + (l.text.len() - rest.len());
+            self.lines[i].indent = column;
+            self.lines[i].text = strip_properties(&rest).to_string();
+            if self.lines[i].text.is_empty() {
+                self.pos = i + 1;
+                items.push(self.parse_nested(indent, false, no));
+            } else {
+                items.push(self.parse_block(column));
+            }
+        }
+        Node {
+            line,
+            value: Value::Sequence(items),
+        }
+    }
+
+    /// The value after `key:` (or `- `) on line `no`; `parent` is the key's indent
+    fn parse_inline(&mut self, parent: usize, text: &str, no: usize) -> Node {
+        let text = strip_properties(text);
+        if text.is_empty() {
+            return self.parse_nested(parent, true, no);
+        }
+        if text.starts_with('|') || text.starts_with('>') {
+            return self.parse_block_scalar(parent, no);
+        }
+        if text.starts_with('[') || (text.starts_with('{') && !text.starts_with("{{")) {
+            return self.parse_flow(text, no);
+        }
+        Node {
+            line: no,
+            value: scalar(text),
+        }
+    }
+
+    /// A value on the lines below its key
+    fn parse_nested(&mut self, parent: usize, allow_same_indent_seq: bool, no: usize) -> Node {
+        let mut templated = false;
+        while let Some(l) = self.lines.get(self.pos) {
+            match l.kind {
+                LineKind::Blank => self.pos += 1,
+                LineKind::Template if l.indent > parent => {
+                    templated = true;
+                    self.pos += 1;
+                }
+                _ => break,
+            }
+        }
+        if let Some(i) = self.peek() {
+            let l = &self.lines[i];
+            let nested = l.indent > parent
+                || (allow_same_indent_seq && l.indent == parent && is_seq_item(&l.text));
+            if nested {
+                let indent = l.indent;
+                return self.parse_block(indent);
+            }
+        }
+        Node {
+            line: no,
+            value: if templated {
+                Value::Templated
+            } else {
+                Value::Null
+            },
+        }
+    }
+
+    /// `|` / `>` body: every following line indented past the key, or empty
+    fn parse_block_scalar(&mut self, parent: usize, no: usize) -> Node {
+        let mut body: Vec<&str> = Vec::new();
+        while let Some(l) = self.lines.get(self.pos) {
+            if !l.raw.trim().is_empty() && l.indent <= parent {
+                break;
+            }
+            body.push(l.raw);
+            self.pos += 1;
+        }
+        while body.last().is_some_and(|l| l.trim().is_empty()) {
+            body.pop();
+        }
+        let strip = body
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.len() - l.trim_start_matches(' ').len())
+            .min()
+            .unwrap_or(0);
+        let text = body
+            .iter()
+            .map(|l| l.get(strip..).unwrap_or(""))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Node {
+            line: no,
+            value: Value::Scalar(text),
+        }
+    }
+
+    /// A flow collection, joining following lines until brackets balance
+    fn parse_flow(&mut self, first: &str, no: usize) -> Node {
+        let mut text = first.to_string();
+        while flow_depth(&text) > 0 {
+            let Some(l) = self.lines.get(self.pos) else {
+                break;
+            };
+            text.push(' ');
+            text.push_str(l.text.trim());
+            self.pos += 1;
+        }
+        let mut flow = Flow {
+            text: &text,
+            pos: 0,
+            line: no,
+        };
+        flow.value()
+    }
+}
+
+// ── Flow collections ─────────────────────────────────────────────
+
+/// Open `[` / `{` minus closing brackets, ignoring quotes and templates
+fn flow_depth(text: &str) -> i32 {
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '{') if chars.peek() == Some(&'{') => {
+                chars.next();
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                }
+                chars.next_if_eq(&'}');
+            }
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth -= 1,
+            _ => {}
+        }
+    }
+    depth
+}
+
+struct Flow<'a> {
+    text: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Flow<'a> {
+    fn node(&self, value: Value) -> Node {
+        Node {
+            line: self.line,
+            value,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_ws();
+        if self.rest().starts_with(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self) -> Node {
+        self.skip_ws();
+        if self.rest().starts_with('[') {
+            self.pos += 1;
+            let mut items = Vec::new();
+            while !self.rest().is_empty() && !self.eat(']') {
+                let start = self.pos;
+                items.push(self.value());
+                if !self.eat(',') && self.pos == start {
+                    break;
+                }
+            }
+            self.node(Value::Sequence(items))
+        } else if self.rest().starts_with('{') && !self.rest().starts_with("{{") {
+            self.pos += 1;
+            let mut entries = Vec::new();
+            while !self.rest().is_empty() && !self.eat('}') {
+                let start = self.pos;
+                let key = self.token(true);
+                let value = if self.eat(':') {
+                    self.value()
+                } else {
+                    self.node(Value::Null)
+                };
+                entries.push(Entry {
+                    key: match scalar(&key) {
+                        Value::Scalar(k) => k,
+                        _ => String::new(),
+                    },
+                    line: self.line,
+                    value,
+                });
+                if !self.eat(',') && self.pos == start {
+                    break;
+                }
+            }
+            self.node(Value::Mapping(entries))
+        } else {
+            let token = self.token(false);
+            self.node(scalar(&token))
+        }
+    }
+
+    /// A quoted or plain scalar up to the next flow indicator
+    fn token(&mut self, key: bool) -> String {
+        self.skip_ws();
+        let rest = self.rest();
+        if let Some(q) = rest.chars().next().filter(|c| *c == '"' || *c == '\'') {
+            let end = rest[1..].find(q).map_or(rest.len(), |e| e + 2);
+            self.pos += end;
+            return rest[..end].to_string();
+        }
+        let bytes = rest.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i..].starts_with(b"{{") {
+                i += bytes[i..]
+                    .windows(2)
+                    .position(|w| w == b"}}")
+                    .map_or(bytes.len() - i, |e| e + 2);
+                continue;
+            }
+            let b = bytes[i];
+            if matches!(b, b',' | b']' | b'}') {
+                break;
+            }
+            if key && b == b':' && matches!(bytes.get(i + 1), None | Some(b' ' | b',' | b'}')) {
+                break;
+            }
+            i += 1;
+        }
+        self.pos += i;
+        rest[..i].trim().to_string()
+    }
+}
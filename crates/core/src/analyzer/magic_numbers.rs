@@ -9,14 +9,23 @@
 //! false-positive contexts (array indices in tests, version tuples, port 80/443,
 //! HTTP status codes with clear surrounding context, etc.).
 //!
+//! Python and TypeScript sources additionally get an AST pass (see
+//! [`tunables`]) that flags hardcoded timeouts, retry counts, pool sizes and
+//! TTLs at configuration-sensitive call sites. Lines it reports are not
+//! repeated by the generic scan.
+//!
 //! Disabled by default (`modules.magic_numbers = false`).
 
+pub mod tunables;
+
 use crate::analyzer::{make_finding, Analyzer};
-use crate::config::RevetConfig;
+use crate::config::{RevetConfig, TunablePatternConfig};
 use crate::finding::{Finding, FixKind, Severity};
 use regex::Regex;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
+use tunables::TunablePattern;
 
 // ── Skip list ─────────────────────────────────────────────────────────────────
 
@@ -59,11 +68,24 @@ fn skip_line_re() -> &'static Regex {
 
 // ── Analyzer ──────────────────────────────────────────────────────────────────
 
-pub struct MagicNumbersAnalyzer;
+pub struct MagicNumbersAnalyzer {
+    /// Configured patterns first, then the built-in table
+    patterns: Vec<TunablePattern>,
+}
 
 impl MagicNumbersAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self::with_patterns(&[])
+    }
+
+    /// Create an analyzer that also recognises `[[magic_numbers.patterns]]`
+    pub fn with_patterns(extra: &[TunablePatternConfig]) -> Self {
+        let mut patterns: Vec<TunablePattern> = extra
+            .iter()
+            .filter_map(TunablePattern::from_config)
+            .collect();
+        patterns.extend(tunables::builtin_patterns());
+        Self { patterns }
     }
 
     fn should_scan(path: &Path) -> bool {
@@ -74,19 +96,26 @@ impl MagicNumbersAnalyzer {
         !SKIP_EXTENSIONS.contains(&ext.as_str())
     }
 
-    fn scan_file(path: &Path) -> Vec<Finding> {
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return Vec::new(),
-        };
+    fn scan_file(&self, path: &Path) -> Vec<Finding> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => self.scan_source(path, &content),
+            Err(_) => Vec::new(),
+        }
+    }
 
+    fn scan_source(&self, path: &Path, content: &str) -> Vec<Finding> {
         let re = magic_number_re();
         let skip = skip_line_re();
-        let mut findings = Vec::new();
+        let mut findings = if tunables::supports(path) {
+            tunables::scan(path, content, &self.patterns)
+        } else {
+            Vec::new()
+        };
+        let tunable_lines: HashSet<usize> = findings.iter().map(|f| f.line).collect();
 
         for (line_num, line) in content.lines().enumerate() {
             // Skip comment lines, constant declarations, and other low-noise contexts
-            if skip.is_match(line) {
+            if skip.is_match(line) || tunable_lines.contains(&(line_num + 1)) {
                 continue;
             }
 
@@ -141,14 +170,33 @@ impl Analyzer for MagicNumbersAnalyzer {
         config.modules.magic_numbers
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("magic_numbers")
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Self::should_scan(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
     fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
         let mut findings = Vec::new();
         for file in files {
             if !Self::should_scan(file) {
                 continue;
             }
-            findings.extend(Self::scan_file(file));
+            findings.extend(self.scan_file(file));
         }
         findings
     }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        if !Self::should_scan(path) {
+            return Vec::new();
+        }
+        self.scan_source(path, content)
+    }
 }
@@ -0,0 +1,648 @@
+//! Configuration-sensitive literals — hardcoded timeouts, retry counts, pool
+//! sizes, cache TTLs and backoff intervals
+//!
+//! Unlike the line-based magic number scan, this pass parses Python and
+//! TypeScript with tree-sitter so it can see *which parameter* a literal is
+//! bound to. Two shapes are flagged:
+//!
+//! - numeric keyword arguments (`requests.get(url, timeout=30)`) and object
+//!   properties in call arguments (`axios.create({ timeout: 5000 })`) that
+//!   match the pattern table
+//! - module-level numeric constants whose name marks them as a tunable
+//!   (`MAX_RETRIES = 5`, `const REQUEST_TIMEOUT_MS = 30000`)
+//!
+//! Positional arguments are never flagged — `sleep(30)` carries no parameter
+//! name to classify it. Values outside a pattern's sanity bounds escalate
+//! from Info to Warning.
+
+use crate::analyzer::make_finding;
+use crate::config::TunablePatternConfig;
+use crate::finding::{Finding, FixKind, Severity};
+use crate::parser::grammar::{language_for, Family};
+use std::path::Path;
+use tree_sitter::Node;
+
+/// What a tunable controls
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TunableKind {
+    Timeout,
+    Retries,
+    PoolSize,
+    Ttl,
+    Backoff,
+}
+
+impl TunableKind {
+    /// Parse a config `kind` value
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "timeout" => Some(Self::Timeout),
+            "retries" => Some(Self::Retries),
+            "pool_size" => Some(Self::PoolSize),
+            "ttl" => Some(Self::Ttl),
+            "backoff" => Some(Self::Backoff),
+            _ => None,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::Retries => "retry count",
+            Self::PoolSize => "pool size",
+            Self::Ttl => "cache TTL",
+            Self::Backoff => "backoff interval",
+        }
+    }
+
+    /// Durations are normalised to seconds before bounds are checked
+    fn is_duration(&self) -> bool {
+        matches!(self, Self::Timeout | Self::Ttl | Self::Backoff)
+    }
+
+    /// Default sanity bounds `(min, max)`
+    fn bounds(&self) -> (Option<f64>, Option<f64>) {
+        match self {
+            Self::Timeout => (None, Some(300.0)),
+            Self::Retries => (None, Some(10.0)),
+            Self::PoolSize => (Some(1.0), Some(1000.0)),
+            Self::Ttl => (None, None),
+            Self::Backoff => (None, Some(300.0)),
+        }
+    }
+}
+
+/// Time unit of a duration literal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Millis,
+    Seconds,
+    Minutes,
+    Hours,
+}
+
+impl Unit {
+    /// Parse a config `unit` value or a trailing name word (`timeout_ms`)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "ms" | "msec" | "millis" | "milliseconds" => Some(Self::Millis),
+            "s" | "sec" | "secs" | "seconds" => Some(Self::Seconds),
+            "min" | "mins" | "minutes" => Some(Self::Minutes),
+            "h" | "hours" => Some(Self::Hours),
+            _ => None,
+        }
+    }
+
+    fn to_seconds(self, value: f64) -> f64 {
+        match self {
+            Self::Millis => value / 1000.0,
+            Self::Seconds => value,
+            Self::Minutes => value * 60.0,
+            Self::Hours => value * 3600.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lang {
+    Python,
+    TypeScript,
+}
+
+impl Lang {
+    fn of(family: Family) -> Option<Self> {
+        match family {
+            Family::Python => Some(Self::Python),
+            Family::Js => Some(Self::TypeScript),
+            _ => None,
+        }
+    }
+
+    /// Unit assumed for durations whose name carries none
+    fn default_unit(&self) -> Unit {
+        match self {
+            Self::Python => Unit::Seconds,
+            Self::TypeScript => Unit::Millis,
+        }
+    }
+}
+
+/// A parameter name that binds a tunable
+#[derive(Debug, Clone)]
+pub struct TunablePattern {
+    language: Option<Lang>,
+    /// Callee name (last path segment); `None` matches any call
+    function: Option<String>,
+    parameter: String,
+    kind: TunableKind,
+    unit: Option<Unit>,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl TunablePattern {
+    fn builtin(language: Lang, function: Option<&str>, parameter: &str, kind: TunableKind) -> Self {
+        let (min, max) = kind.bounds();
+        Self {
+            language: Some(language),
+            function: function.map(String::from),
+            parameter: parameter.to_string(),
+            kind,
+            unit: None,
+            min,
+            max,
+        }
+    }
+
+    /// Build from a `[[magic_numbers.patterns]]` entry; `None` if invalid
+    pub fn from_config(cfg: &TunablePatternConfig) -> Option<Self> {
+        let kind = TunableKind::parse(&cfg.kind)?;
+        let language = match cfg.language.as_deref() {
+            None => None,
+            Some("python") => Some(Lang::Python),
+            Some("typescript") => Some(Lang::TypeScript),
+            Some(_) => return None,
+        };
+        let unit = match &cfg.unit {
+            Some(u) => Some(Unit::parse(u)?),
+            None => None,
+        };
+        let (min, max) = kind.bounds();
+        Some(Self {
+            language,
+            function: cfg.function.clone(),
+            parameter: cfg.parameter.clone(),
+            kind,
+            unit,
+            min: cfg.min.or(min),
+            max: cfg.max.or(max),
+        })
+    }
+
+    fn matches(&self, lang: Lang, callee: &str, parameter: &str) -> bool {
+        self.language.is_none_or(|l| l == lang)
+            && self.parameter == parameter
+            && self.function.as_deref().is_none_or(|f| f == callee)
+    }
+}
+
+/// The built-in pattern table — deliberately conservative
+pub fn builtin_patterns() -> Vec<TunablePattern> {
+    use Lang::{Python as Py, TypeScript as Ts};
+    use TunableKind::*;
+
+    let any: &[(Lang, Option<&str>, &str, TunableKind)] = &[
+        // ── Python ───────────────────────────────────────────────
+        (Py, None, "timeout", Timeout),
+        (Py, None, "connect_timeout", Timeout),
+        (Py, None, "read_timeout", Timeout),
+        (Py, None, "socket_timeout", Timeout),
+        (Py, None, "socket_connect_timeout", Timeout),
+        (Py, None, "pool_timeout", Timeout),
+        (Py, None, "max_retries", Retries),
+        (Py, None, "retries", Retries),
+        (Py, None, "max_attempts", Retries),
+        (Py, Some("Retry"), "total", Retries),
+        (Py, Some("Retry"), "connect", Retries),
+        (Py, Some("Retry"), "read", Retries),
+        (Py, None, "backoff_factor", Backoff),
+        (Py, None, "pool_size", PoolSize),
+        (Py, None, "max_overflow", PoolSize),
+        (Py, None, "pool_maxsize", PoolSize),
+        (Py, None, "pool_connections", PoolSize),
+        (Py, None, "max_connections", PoolSize),
+        (Py, None, "max_workers", PoolSize),
+        (Py, None, "maxconn", PoolSize),
+        (Py, None, "ttl", Ttl),
+        (Py, None, "cache_ttl", Ttl),
+        (Py, Some("set"), "ex", Ttl),
+        // ── TypeScript / JavaScript ──────────────────────────────
+        (Ts, None, "timeout", Timeout),
+        (Ts, None, "connectTimeout", Timeout),
+        (Ts, None, "connectionTimeout", Timeout),
+        (Ts, None, "socketTimeout", Timeout),
+        (Ts, None, "requestTimeout", Timeout),
+        (Ts, None, "idleTimeoutMillis", Timeout),
+        (Ts, None, "connectionTimeoutMillis", Timeout),
+        (Ts, None, "acquireTimeoutMillis", Timeout),
+        (Ts, None, "retries", Retries),
+        (Ts, None, "maxRetries", Retries),
+        (Ts, None, "maxAttempts", Retries),
+        (Ts, None, "retryDelay", Backoff),
+        (Ts, None, "poolSize", PoolSize),
+        (Ts, None, "connectionLimit", PoolSize),
+        (Ts, None, "maxSockets", PoolSize),
+        (Ts, None, "maxConnections", PoolSize),
+        (Ts, Some("Pool"), "max", PoolSize),
+        (Ts, Some("createPool"), "max", PoolSize),
+        (Ts, None, "ttl", Ttl),
+        (Ts, None, "stdTTL", Ttl),
+    ];
+    any.iter()
+        .map(|(lang, function, parameter, kind)| {
+            TunablePattern::builtin(*lang, *function, parameter, *kind)
+        })
+        .collect()
+}
+
+/// Returns true for test files, which legitimately hardcode short timeouts
+fn is_test_file(path: &Path) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    if name.starts_with("test_")
+        || name.ends_with("_test.py")
+        || name == "conftest.py"
+        || [".test.", ".spec."].iter().any(|m| name.contains(m))
+    {
+        return true;
+    }
+    path.components()
+        .any(|c| c.as_os_str() == "tests" || c.as_os_str() == "__tests__")
+}
+
+/// Whether `path` is a language this pass understands
+pub fn supports(path: &Path) -> bool {
+    Family::of(path).and_then(Lang::of).is_some()
+}
+
+/// Scan one Python/TypeScript source for hardcoded tunables
+pub fn scan(path: &Path, content: &str, patterns: &[TunablePattern]) -> Vec<Finding> {
+    let Some(grammar) = language_for(path) else {
+        return Vec::new();
+    };
+    let Some(lang) = Lang::of(grammar.family) else {
+        return Vec::new();
+    };
+    if is_test_file(path) {
+        return Vec::new();
+    }
+
+    let Some(tree) = grammar.parse(content) else {
+        return Vec::new();
+    };
+
+    let mut scanner = Scanner {
+        path,
+        source: content.as_bytes(),
+        lang,
+        patterns,
+        findings: Vec::new(),
+    };
+    let root = tree.root_node();
+    scanner.module_constants(root);
+    scanner.walk(root);
+    scanner.findings.sort_by_key(|f| f.line);
+    scanner.findings
+}
+
+struct Scanner<'a> {
+    path: &'a Path,
+    source: &'a [u8],
+    lang: Lang,
+    patterns: &'a [TunablePattern],
+    findings: Vec<Finding>,
+}
+
+impl Scanner<'_> {
+    fn text(&self, node: Node) -> &str {
+        node.utf8_text(self.source).unwrap_or("")
+    }
+
+    /// Visit every call site in the tree
+    fn walk(&mut self, node: Node) {
+        match (self.lang, node.kind()) {
+            (Lang::Python, "call") => self.python_call(node),
+            (Lang::TypeScript, "call_expression") => self.ts_call(node, "function"),
+            (Lang::TypeScript, "new_expression") => self.ts_call(node, "constructor"),
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.walk(child);
+        }
+    }
+
+    fn python_call(&mut self, call: Node) {
+        let callee = call
+            .child_by_field_name("function")
+            .map(|f| self.callee_name(f))
+            .unwrap_or_default();
+        let Some(args) = call.child_by_field_name("arguments") else {
+            return;
+        };
+        let mut cursor = args.walk();
+        for arg in args.named_children(&mut cursor) {
+            if arg.kind() != "keyword_argument" {
+                continue;
+            }
+            let (Some(name), Some(value)) = (
+                arg.child_by_field_name("name"),
+                arg.child_by_field_name("value"),
+            ) else {
+                continue;
+            };
+            let name = self.text(name).to_string();
+            self.check_argument(&callee, &name, value, arg);
+        }
+    }
+
+    fn ts_call(&mut self, call: Node, callee_field: &str) {
+        let callee = call
+            .child_by_field_name(callee_field)
+            .map(|f| self.callee_name(f))
+            .unwrap_or_default();
+        let Some(args) = call.child_by_field_name("arguments") else {
+            return;
+        };
+        let mut cursor = args.walk();
+        for arg in args.named_children(&mut cursor) {
+            if arg.kind() == "object" {
+                self.ts_object(&callee, arg);
+            }
+        }
+    }
+
+    /// Properties of an options object, including nested option groups
+    fn ts_object(&mut self, callee: &str, object: Node) {
+        let mut cursor = object.walk();
+        for pair in object.named_children(&mut cursor) {
+            if pair.kind() != "pair" {
+                continue;
+            }
+            let (Some(key), Some(value)) = (
+                pair.child_by_field_name("key"),
+                pair.child_by_field_name("value"),
+            ) else {
+                continue;
+            };
+            if value.kind() == "object" {
+                self.ts_object(callee, value);
+                continue;
+            }
+            let key = self.text(key).trim_matches(['"', '\'']).to_string();
+            self.check_argument(callee, &key, value, pair);
+        }
+    }
+
+    /// Last segment of a callee expression (`requests.get` → `get`)
+    fn callee_name(&self, node: Node) -> String {
+        let last = match node.kind() {
+            "attribute" => node.child_by_field_name("attribute"),
+            "member_expression" => node.child_by_field_name("property"),
+            _ => Some(node),
+        };
+        last.map(|n| self.text(n).to_string()).unwrap_or_default()
+    }
+
+    fn check_argument(&mut self, callee: &str, name: &str, value: Node, site: Node) {
+        let Some(number) = self.numeric_literal(value) else {
+            return;
+        };
+        let Some(pattern) = self
+            .patterns
+            .iter()
+            .find(|p| p.matches(self.lang, callee, name))
+        else {
+            return;
+        };
+        let unit = pattern.unit.or_else(|| name_unit(name));
+        let call = if callee.is_empty() {
+            "call".to_string()
+        } else {
+            format!("`{}` call", callee)
+        };
+        let subject = format!(
+            "{} `{}` in {}",
+            pattern.kind.label(),
+            self.text(site)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+            call
+        );
+        let (min, max) = (pattern.min, pattern.max);
+        self.report(site, subject, pattern.kind, number, unit, min, max);
+    }
+
+    /// Top-level numeric constants named like tunables
+    fn module_constants(&mut self, root: Node) {
+        let mut cursor = root.walk();
+        for stmt in root.named_children(&mut cursor) {
+            match self.lang {
+                Lang::Python if stmt.kind() == "expression_statement" => {
+                    let Some(assign) = stmt.named_child(0).filter(|n| n.kind() == "assignment")
+                    else {
+                        continue;
+                    };
+                    let (Some(left), Some(right)) = (
+                        assign.child_by_field_name("left"),
+                        assign.child_by_field_name("right"),
+                    ) else {
+                        continue;
+                    };
+                    let name = self.text(left).to_string();
+                    // Python constants are UPPER_CASE by convention
+                    if left.kind() == "identifier" && !name.chars().any(|c| c.is_lowercase()) {
+                        self.check_constant(&name, right, assign);
+                    }
+                }
+                Lang::TypeScript => {
+                    let decl = if stmt.kind() == "export_statement" {
+                        stmt.child_by_field_name("declaration")
+                    } else {
+                        Some(stmt)
+                    };
+                    let Some(decl) = decl.filter(|d| d.kind() == "lexical_declaration") else {
+                        continue;
+                    };
+                    if !self.text(decl).starts_with("const") {
+                        continue;
+                    }
+                    let mut inner = decl.walk();
+                    for declarator in decl.named_children(&mut inner) {
+                        let (Some(name), Some(value)) = (
+                            declarator.child_by_field_name("name"),
+                            declarator.child_by_field_name("value"),
+                        ) else {
+                            continue;
+                        };
+                        let name = self.text(name).to_string();
+                        self.check_constant(&name, value, declarator);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_constant(&mut self, name: &str, value: Node, site: Node) {
+        let Some(number) = self.numeric_literal(value) else {
+            return;
+        };
+        let Some(kind) = name_kind(name) else {
+            return;
+        };
+        let subject = format!("{} constant `{}`", kind.label(), name);
+        let (min, max) = kind.bounds();
+        self.report(site, subject, kind, number, name_unit(name), min, max);
+    }
+
+    /// Integer/float literal value, allowing a leading minus and `_` separators
+    fn numeric_literal(&self, node: Node) -> Option<f64> {
+        match node.kind() {
+            "integer" | "float" | "number" => {
+                let text = self.text(node).replace('_', "");
+                if text.starts_with("0x") || text.starts_with("0o") || text.starts_with("0b") {
+                    return None;
+                }
+                text.trim_end_matches(['n', 'j', 'J']).parse().ok()
+            }
+            "unary_operator" | "unary_expression" => {
+                let operand = node
+                    .child_by_field_name("argument")
+                    .or_else(|| node.child_by_field_name("operand"))?;
+                let op = node.child(0)?;
+                if self.text(op) == "-" {
+                    self.numeric_literal(operand).map(|v| -v)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn report(
+        &mut self,
+        site: Node,
+        subject: String,
+        kind: TunableKind,
+        value: f64,
+        unit: Option<Unit>,
+        min: Option<f64>,
+        max: Option<f64>,
+    ) {
+        let (normalized, shown) = if kind.is_duration() {
+            let unit = unit.unwrap_or(self.lang.default_unit());
+            let secs = unit.to_seconds(value);
+            (secs, format!("{}s", trim_float(secs)))
+        } else {
+            (value, trim_float(value))
+        };
+
+        let out_of_bounds = if max.is_some_and(|m| normalized > m) {
+            Some(format!(
+                "{} exceeds the sanity bound of {}",
+                shown,
+                bound(kind, max)
+            ))
+        } else if min.is_some_and(|m| normalized < m) {
+            Some(format!(
+                "{} is below the sanity bound of {}",
+                shown,
+                bound(kind, min)
+            ))
+        } else {
+            None
+        };
+
+        let (severity, message) = match out_of_bounds {
+            Some(detail) => (
+                Severity::Warning,
+                format!("Hardcoded {} — {}", subject, detail),
+            ),
+            None => (
+                Severity::Info,
+                format!(
+                    "Hardcoded {} — consider sourcing it from configuration",
+                    subject
+                ),
+            ),
+        };
+        let suggestion = match self.lang {
+            Lang::Python => "Read the value from settings or the environment (e.g. `int(os.environ.get(\"HTTP_TIMEOUT\", \"30\"))`) so it can be tuned without a code change",
+            Lang::TypeScript => "Read the value from config or the environment (e.g. `Number(process.env.HTTP_TIMEOUT_MS ?? 30000)`) so it can be tuned without a code change",
+        };
+
+        self.findings.push(make_finding(
+            severity,
+            message,
+            self.path.to_path_buf(),
+            site.start_position().row + 1,
+            Some(suggestion.to_string()),
+            Some(FixKind::Suggestion),
+        ));
+    }
+}
+
+fn bound(kind: TunableKind, value: Option<f64>) -> String {
+    let v = trim_float(value.unwrap_or_default());
+    if kind.is_duration() {
+        format!("{}s", v)
+    } else {
+        v
+    }
+}
+
+fn trim_float(v: f64) -> String {
+    if v.fract() == 0.0 {
+        format!("{}", v as i64)
+    } else {
+        format!("{}", (v * 1000.0).round() / 1000.0)
+    }
+}
+
+/// Split `MAX_RETRY_COUNT` / `requestTimeoutMs` into lowercase words
+pub(crate) fn name_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c == '_' || c == '-' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.push(c.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Duration unit from the last word of a name (`timeout_ms`, `ttlSeconds`)
+fn name_unit(name: &str) -> Option<Unit> {
+    name_words(name).last().and_then(|w| Unit::parse(w))
+}
+
+/// Classify a constant by name; `None` unless it clearly names a tunable
+fn name_kind(name: &str) -> Option<TunableKind> {
+    let words = name_words(name);
+    let has = |w: &str| words.iter().any(|x| x == w);
+
+    if has("timeout") {
+        Some(TunableKind::Timeout)
+    } else if has("backoff") || (has("retry") && (has("delay") || has("interval"))) {
+        Some(TunableKind::Backoff)
+    } else if has("retries")
+        || (has("retry") && (has("count") || has("limit") || has("max")))
+        || (has("max") && has("attempts"))
+    {
+        Some(TunableKind::Retries)
+    } else if (has("pool") && (has("size") || has("max")))
+        || (has("max") && (has("connections") || has("workers")))
+    {
+        Some(TunableKind::PoolSize)
+    } else if has("ttl") || (has("cache") && (has("expiry") || has("expiration"))) {
+        Some(TunableKind::Ttl)
+    } else {
+        None
+    }
+}
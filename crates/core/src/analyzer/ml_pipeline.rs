@@ -227,11 +227,13 @@ impl MlPipelineAnalyzer {
 
     /// Scan a single file for ML pipeline patterns
     fn scan_file(path: &Path) -> Vec<Finding> {
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return Vec::new(),
-        };
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::scan_source(path, &content),
+            Err(_) => Vec::new(),
+        }
+    }
 
+    fn scan_source(path: &Path, content: &str) -> Vec<Finding> {
         let all_patterns = patterns();
         let lines: Vec<&str> = content.lines().collect();
         let mut findings = Vec::new();
@@ -365,6 +367,18 @@ impl Analyzer for MlPipelineAnalyzer {
         config.modules.ml
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("ml")
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Self::should_scan(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
     fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
         let mut findings = Vec::new();
 
@@ -377,4 +391,11 @@ impl Analyzer for MlPipelineAnalyzer {
 
         findings
     }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        if !Self::should_scan(path) {
+            return Vec::new();
+        }
+        Self::scan_source(path, content)
+    }
 }
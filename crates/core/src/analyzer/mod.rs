@@ -4,36 +4,74 @@
 //! [`Finding`]s. Each analyzer is independent and can be enabled/disabled
 //! via `.revet.toml`.
 
+pub mod a11y;
 pub mod async_patterns;
 pub mod circular_imports;
 pub mod command_injection;
 pub mod complexity;
+pub mod concurrency;
+pub mod config_drift;
+pub mod contract;
+pub mod copy;
+pub mod coupling;
 pub mod custom_rules;
 pub mod dead_imports;
+pub mod debug_artifacts;
 pub mod dependency;
+pub mod docs;
 pub mod duplication;
 pub mod error_handling;
+pub mod feature_flags;
+pub mod file_timing;
+pub mod generated;
 pub mod hardcoded_endpoints;
+pub mod hotspots;
+pub mod import_boundaries;
 pub mod infra;
 pub mod insecure_deserialization;
+pub mod kubernetes;
 pub mod magic_numbers;
 pub mod ml_pipeline;
+pub mod numeric;
+pub mod ownership;
+pub mod pagination;
+pub mod partition;
 pub mod path_traversal;
 pub mod react_hooks;
+pub mod refactor;
+pub mod registry;
+pub mod repository;
+pub mod resource_leaks;
+pub mod rules;
 pub mod secret_exposure;
 pub mod sensitive_logging;
+pub mod skew;
 pub mod sql_injection;
 pub mod ssrf;
+pub mod suppressions;
 pub mod test_coverage;
+pub mod test_quality;
 pub mod toolchain;
+pub mod translations;
 pub mod unused_exports;
+pub mod upgrade;
+pub mod websec;
 
-use crate::config::RevetConfig;
+use crate::cache::memo::{AnalysisMemo, MemoRun};
+use crate::calibrate::Metric;
+use crate::cancel::{CancelToken, Cancelled};
+use crate::config::{ModulesConfig, RevetConfig, SeverityOverride};
+use crate::coverage::CoverageLedger;
 use crate::finding::{Finding, FixKind, Severity};
+use crate::git_objects::{Feature, GitObjects};
 use crate::graph::CodeGraph;
+use crate::i18n::Msg;
+use crate::rollout::{Rollout, ShadowModule};
+use crate::suppress::{DirectiveUsage, SuppressedFinding};
+use crate::trace::{self, Decision};
 use rayon::prelude::*;
 use std::path::{Path, PathBuf};
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 /// Per-analyzer timing record returned by `run_all_parallel_timed` and
 /// `run_graph_analyzers_timed`.
@@ -43,6 +81,19 @@ pub struct AnalyzerTiming {
     pub prefix: String,
     pub duration: Duration,
     pub findings: usize,
+    /// Slowest first: the [`file_timing::SLOWEST_FILES`] slowest files, plus any
+    /// over `[performance] slow_file_warn_ms`. Empty unless per-file timing
+    /// was on (see [`AnalyzerDispatcher::with_file_timings`]).
+    pub files: Vec<file_timing::FileTiming>,
+    /// Most bytes the analyzer had allocated at once (`alloc-stats` builds)
+    pub peak_alloc: Option<u64>,
+}
+
+impl AnalyzerTiming {
+    /// The files `--timings=full` lists
+    pub fn slowest_files(&self) -> &[file_timing::FileTiming] {
+        &self.files[..self.files.len().min(file_timing::SLOWEST_FILES)]
+    }
 }
 
 /// Trait for domain-specific analyzers
@@ -59,12 +110,47 @@ pub trait Analyzer: Send + Sync {
     /// Whether this analyzer is enabled given the current config
     fn is_enabled(&self, config: &RevetConfig) -> bool;
 
+    /// `[modules]` name that enables this analyzer (`None` for analyzers
+    /// built from user config, such as custom rules)
+    fn module(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Sub-rule ids this analyzer tags its findings with (`Finding::rule`),
+    /// selectable with `--module module:rule` and `[analyzers.<module>]`
+    fn rules(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     /// Analyze the given files and return findings
     ///
     /// `repo_root` is the absolute path to the repository root, used to
     /// produce relative file paths in findings.
     fn analyze_files(&self, files: &[PathBuf], repo_root: &Path) -> Vec<Finding>;
 
+    /// Whether `analyze_files` scans `path` (used by the coverage report)
+    ///
+    /// Analyzers that pick their own inputs from the repository (toolchain,
+    /// generated code) accept no discovered file.
+    fn accepts(&self, _path: &Path) -> bool {
+        false
+    }
+
+    /// Whether `analyze_files` looks at each file on its own, in the order
+    /// given, so consecutive slices of the files can be analyzed one after
+    /// another (see [`partition`]) with the same result
+    fn per_file(&self) -> bool {
+        false
+    }
+
+    /// Analyze an in-memory buffer as if it were the contents of `path`
+    ///
+    /// Used for unsaved editor buffers. Analyzers that need the whole
+    /// repository (duplication, toolchain) return nothing by default.
+    fn analyze_source(&self, _path: &Path, _content: &str, _repo_root: &Path) -> Vec<Finding> {
+        Vec::new()
+    }
+
     /// Additional file extensions this analyzer needs beyond parser extensions.
     /// Returns extensions with leading dot (e.g., `[".tf", ".yaml"]`).
     fn extra_extensions(&self) -> &[&str] {
@@ -75,6 +161,20 @@ pub trait Analyzer: Send + Sync {
     fn extra_filenames(&self) -> &[&str] {
         &[]
     }
+
+    /// Hidden files this analyzer needs, as file-name globs (e.g.,
+    /// `[".env*"]`). Discovery skips dotfiles otherwise; matched ones are
+    /// only given to content analyzers, never to a language parser.
+    fn extra_dotfiles(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Measure the metrics behind this analyzer's thresholds over `files`
+    /// without comparing them, for `revet calibrate`. Analyzers without a
+    /// tunable threshold measure nothing.
+    fn measure(&self, _files: &[PathBuf], _repo_root: &Path) -> Vec<Metric> {
+        Vec::new()
+    }
 }
 
 /// Trait for graph-based analyzers that query the CodeGraph.
@@ -88,14 +188,56 @@ pub trait GraphAnalyzer: Send + Sync {
     /// Whether this analyzer is enabled given the current config
     fn is_enabled(&self, config: &RevetConfig) -> bool;
 
+    /// `[modules]` name that enables this analyzer
+    fn module(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Sub-rule ids this analyzer tags its findings with (`Finding::rule`)
+    fn rules(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     /// Analyze the code graph and return findings
     fn analyze_graph(&self, graph: &CodeGraph, config: &RevetConfig) -> Vec<Finding>;
+
+    /// Analyze only the nodes of `file`, whose current (possibly unsaved)
+    /// source is `content`, and return findings for that file alone.
+    ///
+    /// Only checks that are local to one file implement this; cross-file
+    /// checks (cycles, test coverage) return nothing by default.
+    fn analyze_file(
+        &self,
+        _graph: &CodeGraph,
+        _file: &Path,
+        _content: &str,
+        _config: &RevetConfig,
+    ) -> Vec<Finding> {
+        Vec::new()
+    }
+
+    /// Measure the metrics behind this analyzer's thresholds over `graph`
+    /// without comparing them, for `revet calibrate`
+    fn measure(&self, _graph: &CodeGraph, _config: &RevetConfig) -> Vec<Metric> {
+        Vec::new()
+    }
+
+    /// Whether the findings depend on the graph and config alone, so an
+    /// [`AnalysisMemo`] may keep them per set of files. Analyzers that also
+    /// read files outside the graph return false.
+    fn memoizable(&self) -> bool {
+        true
+    }
 }
 
 /// Dispatches analysis across all registered analyzers
 pub struct AnalyzerDispatcher {
     analyzers: Vec<Box<dyn Analyzer>>,
     graph_analyzers: Vec<Box<dyn GraphAnalyzer>>,
+    file_timings: bool,
+    rollout: Rollout,
+    git_objects: Option<GitObjects>,
+    memo: Option<AnalysisMemo>,
 }
 
 impl AnalyzerDispatcher {
@@ -112,25 +254,134 @@ impl AnalyzerDispatcher {
                 Box::new(sensitive_logging::SensitiveLoggingAnalyzer::new()),
                 Box::new(hardcoded_endpoints::HardcodedEndpointsAnalyzer::new()),
                 Box::new(magic_numbers::MagicNumbersAnalyzer::new()),
+                Box::new(resource_leaks::ResourceLeaksAnalyzer::new()),
                 Box::new(ml_pipeline::MlPipelineAnalyzer::new()),
                 Box::new(infra::InfraAnalyzer::new()),
+                Box::new(kubernetes::KubernetesAnalyzer::new()),
                 Box::new(react_hooks::ReactHooksAnalyzer::new()),
                 Box::new(async_patterns::AsyncPatternsAnalyzer::new()),
                 Box::new(dependency::DependencyAnalyzer::new()),
                 Box::new(error_handling::ErrorHandlingAnalyzer::new()),
                 Box::new(toolchain::ToolchainAnalyzer::new()),
                 Box::new(duplication::DuplicationAnalyzer::new()),
+                Box::new(skew::SkewAnalyzer::new()),
+                Box::new(translations::TranslationsAnalyzer::new()),
+                Box::new(feature_flags::FeatureFlagsAnalyzer::new()),
+                Box::new(debug_artifacts::DebugArtifactsAnalyzer::new()),
+                Box::new(test_quality::TestQualityAnalyzer::new()),
+                Box::new(copy::CopyAnalyzer::new()),
+                Box::new(numeric::NumericAnalyzer::new()),
+                Box::new(pagination::PaginationAnalyzer::new()),
+                Box::new(a11y::A11yAnalyzer::new()),
+                Box::new(websec::WebsecAnalyzer::new()),
+                Box::new(contract::ContractAnalyzer::new()),
+                Box::new(repository::RepositoryAnalyzer::new()),
             ],
             graph_analyzers: vec![
                 Box::new(unused_exports::UnusedExportsAnalyzer::new()),
                 Box::new(circular_imports::CircularImportsAnalyzer::new()),
+                Box::new(import_boundaries::ImportBoundaryAnalyzer::new()),
                 Box::new(complexity::ComplexityAnalyzer::new()),
                 Box::new(dead_imports::DeadImportsAnalyzer::new()),
                 Box::new(test_coverage::TestCoverageAnalyzer::new()),
+                Box::new(coupling::CouplingAnalyzer::new()),
+                Box::new(docs::DocsAnalyzer::new()),
+                Box::new(concurrency::ConcurrencyAnalyzer::new()),
+                Box::new(upgrade::UpgradeAnalyzer::new()),
             ],
+            file_timings: false,
+            rollout: Rollout::default(),
+            git_objects: None,
+            memo: None,
         }
     }
 
+    /// Also time each file in the timed runs (`--timings=full`); always on
+    /// when `[performance] slow_file_warn_ms` is set
+    pub fn with_file_timings(mut self, enabled: bool) -> Self {
+        self.file_timings = enabled;
+        self
+    }
+
+    /// Run the modules `rollout` samples out in shadow mode: they still run,
+    /// and [`split_shadow`](Self::split_shadow) sets their findings apart
+    pub fn with_rollout(mut self, rollout: Rollout) -> Self {
+        self.rollout = rollout;
+        self
+    }
+
+    pub fn rollout(&self) -> &Rollout {
+        &self.rollout
+    }
+
+    /// Read git objects through the run's `objects`, so blame and churn
+    /// skips in a partial clone add up with the rest of the run's
+    pub fn with_git_objects(mut self, objects: GitObjects) -> Self {
+        self.git_objects = Some(objects);
+        self
+    }
+
+    /// Keep per-file and graph analyzer findings in `memo` and answer
+    /// unchanged files from it (see [`AnalysisMemo`]); `None` analyzes
+    /// every file each run
+    pub fn with_memo(mut self, memo: Option<AnalysisMemo>) -> Self {
+        self.memo = memo;
+        self
+    }
+
+    pub fn memo(&self) -> Option<&AnalysisMemo> {
+        self.memo.as_ref()
+    }
+
+    /// Start a memoized run on `files`, if there is a memo
+    fn memo_run(&self, files: &[PathBuf], config: &RevetConfig) -> Option<MemoRun> {
+        self.memo.as_ref().map(|memo| memo.begin(config, files))
+    }
+
+    /// The run's git objects, or the repository's under `[git]`
+    pub fn git_objects(&self, repo_root: &Path, config: &RevetConfig) -> GitObjects {
+        self.git_objects
+            .clone()
+            .unwrap_or_else(|| GitObjects::open(repo_root, &config.git))
+    }
+
+    /// Split `findings` into the ones to report and the ones of modules in
+    /// shadow mode
+    pub fn split_shadow(&self, findings: Vec<Finding>) -> (Vec<Finding>, Vec<Finding>) {
+        if self.rollout.is_empty() {
+            return (findings, Vec::new());
+        }
+        findings.into_iter().partition(|f| {
+            !self
+                .module_of_finding(f)
+                .is_some_and(|module| self.rollout.is_shadow(module))
+        })
+    }
+
+    /// Per-module counts of the shadow findings `split_shadow` set apart
+    pub fn shadow_summary(&self, shadow: &[Finding]) -> Vec<ShadowModule> {
+        self.rollout
+            .summarize(shadow, |finding| self.module_of_finding(finding))
+    }
+
+    /// `[modules]` name of the analyzer that reported `finding`: the module
+    /// of its qualified sub-rule, else the module of its ID prefix. Imported
+    /// findings belong to no module.
+    pub fn module_of_finding(&self, finding: &Finding) -> Option<&'static str> {
+        if finding.source_tool.is_some() {
+            return None;
+        }
+        if let Some((module, _)) = finding.rule.as_deref().and_then(|r| r.split_once(':')) {
+            return ModulesConfig::NAMES.iter().copied().find(|m| *m == module);
+        }
+        let (prefix, _) = finding.id.rsplit_once('-')?;
+        self.module_of(prefix)
+    }
+
+    fn times_files(&self, config: &RevetConfig) -> bool {
+        self.file_timings || config.performance.slow_file_warn_ms.is_some()
+    }
+
     /// Create a dispatcher with built-in analyzers plus custom rules from config
     pub fn new_with_config(config: &RevetConfig) -> Self {
         let mut dispatcher = Self::new();
@@ -143,9 +394,228 @@ impl AnalyzerDispatcher {
                 config.modules.duplication_min_lines,
             )));
 
-        let custom = custom_rules::CustomRulesAnalyzer::from_config(config);
-        if custom.is_enabled(config) {
-            dispatcher.analyzers.push(Box::new(custom));
+        // Secret findings honour `[output] redact_secrets`, add
+        // `[[analyzers.secrets.patterns]]` checked at `min_entropy` and
+        // classify test code with `[files]`
+        dispatcher.analyzers.retain(|a| a.finding_prefix() != "SEC");
+        dispatcher.analyzers.insert(
+            0,
+            Box::new(
+                secret_exposure::SecretExposureAnalyzer::with_redaction(
+                    config.output.redact_secrets,
+                )
+                .with_patterns(&config.analyzers.secrets.patterns)
+                .with_min_entropy(config.analyzers.secrets.min_entropy)
+                .with_files(&config.files),
+            ),
+        );
+
+        // Magic numbers also recognise `[[magic_numbers.patterns]]`
+        dispatcher
+            .analyzers
+            .retain(|a| a.finding_prefix() != "MAGIC");
+        dispatcher.analyzers.push(Box::new(
+            magic_numbers::MagicNumbersAnalyzer::with_patterns(&config.magic_numbers.patterns),
+        ));
+
+        // Dependency hygiene applies version-dependent checks to the
+        // Python target
+        dispatcher.analyzers.retain(|a| a.finding_prefix() != "DEP");
+        dispatcher.analyzers.push(Box::new(
+            dependency::DependencyAnalyzer::new().with_targets(&config.targets),
+        ));
+
+        // Resource leaks also recognise `[resource_leaks] closeable_types`
+        // and follow the Java target
+        dispatcher.analyzers.retain(|a| a.finding_prefix() != "RES");
+        dispatcher.analyzers.push(Box::new(
+            resource_leaks::ResourceLeaksAnalyzer::with_types(
+                &config.resource_leaks.closeable_types,
+            )
+            .with_targets(&config.targets),
+        ));
+
+        // Sensitive logging also recognises `[sensitive_logging]` names
+        dispatcher.analyzers.retain(|a| a.finding_prefix() != "LOG");
+        dispatcher.analyzers.push(Box::new(
+            sensitive_logging::SensitiveLoggingAnalyzer::with_names(
+                &config.sensitive_logging.sensitive_names,
+                &config.sensitive_logging.allow_names,
+            ),
+        ));
+
+        // Kubernetes rules follow `[kubernetes]`; when on, it replaces the
+        // infra analyzer's line-based manifest checks
+        dispatcher.analyzers.retain(|a| a.finding_prefix() != "K8S");
+        dispatcher
+            .analyzers
+            .push(Box::new(kubernetes::KubernetesAnalyzer::with_rules(
+                &config.kubernetes,
+            )));
+        if config.modules.kubernetes {
+            dispatcher
+                .analyzers
+                .retain(|a| a.finding_prefix() != "INFRA");
+            dispatcher
+                .analyzers
+                .push(Box::new(infra::InfraAnalyzer::without_kubernetes()));
+        }
+
+        // Skew honours `[analyzers.skew]` thresholds, keys and exceptions
+        dispatcher
+            .analyzers
+            .retain(|a| a.finding_prefix() != "SKEW");
+        dispatcher
+            .analyzers
+            .push(Box::new(skew::SkewAnalyzer::with_config(
+                &config.analyzers.skew,
+            )));
+
+        // Translations honour `[analyzers.i18n]` catalogs and lookup tables
+        dispatcher
+            .analyzers
+            .retain(|a| a.finding_prefix() != "I18N");
+        dispatcher
+            .analyzers
+            .push(Box::new(translations::TranslationsAnalyzer::with_config(
+                &config.analyzers.i18n,
+            )));
+
+        // Feature flags honour `[analyzers.feature_flags]` inventory and SDKs
+        dispatcher
+            .analyzers
+            .retain(|a| a.finding_prefix() != "FLAG");
+        dispatcher
+            .analyzers
+            .push(Box::new(feature_flags::FeatureFlagsAnalyzer::with_config(
+                &config.analyzers.feature_flags,
+            )));
+
+        // Debug artifacts honour `[analyzers.debug_artifacts]` patterns and
+        // severities
+        dispatcher.analyzers.retain(|a| a.finding_prefix() != "DBG");
+        dispatcher.analyzers.push(Box::new(
+            debug_artifacts::DebugArtifactsAnalyzer::with_config(&config.analyzers.debug_artifacts),
+        ));
+
+        // Test quality honours `[analyzers.test_quality]` and the test paths
+        // of `[files]`
+        dispatcher
+            .analyzers
+            .retain(|a| a.finding_prefix() != "TEST");
+        dispatcher.analyzers.push(Box::new(
+            test_quality::TestQualityAnalyzer::with_config(&config.analyzers.test_quality)
+                .with_files(&config.files),
+        ));
+
+        // Copy checks honour `[analyzers.copy]` terms, paths and severities
+        dispatcher
+            .analyzers
+            .retain(|a| a.finding_prefix() != "COPY");
+        dispatcher
+            .analyzers
+            .push(Box::new(copy::CopyAnalyzer::with_config(
+                &config.analyzers.copy,
+            )));
+
+        // Numeric checks honour `[analyzers.numeric]` names, the test paths
+        // of `[files]` and the ECMAScript target
+        dispatcher.analyzers.retain(|a| a.finding_prefix() != "NUM");
+        dispatcher.analyzers.push(Box::new(
+            numeric::NumericAnalyzer::with_config(&config.analyzers.numeric)
+                .with_files(&config.files)
+                .with_targets(&config.targets),
+        ));
+
+        // Pagination honours `[analyzers.pagination]` small tables
+        dispatcher
+            .analyzers
+            .retain(|a| a.finding_prefix() != "PAGE");
+        dispatcher
+            .analyzers
+            .push(Box::new(pagination::PaginationAnalyzer::with_config(
+                &config.analyzers.pagination,
+            )));
+
+        // Web security checks honour `[analyzers.websec]` severities
+        dispatcher
+            .analyzers
+            .retain(|a| a.finding_prefix() != "WEBSEC");
+        dispatcher
+            .analyzers
+            .push(Box::new(websec::WebsecAnalyzer::with_config(
+                &config.analyzers.websec,
+            )));
+
+        // API contract checks honour `[analyzers.contract]` and follow the
+        // clients `[[analyzers.generated]]` pairs with a spec
+        dispatcher
+            .analyzers
+            .retain(|a| a.finding_prefix() != "CONTRACT");
+        dispatcher.analyzers.push(Box::new(
+            contract::ContractAnalyzer::with_config(&config.analyzers.contract)
+                .with_generated(&config.analyzers.generated),
+        ));
+
+        // Repository checks follow `[analyzers.repository]` and read the
+        // security switch and the baseline location
+        dispatcher
+            .analyzers
+            .retain(|a| a.finding_prefix() != "REPO");
+        dispatcher
+            .analyzers
+            .push(Box::new(repository::RepositoryAnalyzer::with_config(
+                config,
+            )));
+
+        // One-finding-per-line analyzers skip deselected sub-rules while
+        // scanning, so a later pattern can still report the line
+        dispatcher
+            .analyzers
+            .retain(|a| !matches!(a.finding_prefix(), "ERR" | "ASYNC" | "HOOKS"));
+        dispatcher.analyzers.extend([
+            Box::new(error_handling::ErrorHandlingAnalyzer::with_rules(
+                config.rule_selection("error_handling"),
+            )) as Box<dyn Analyzer>,
+            Box::new(
+                async_patterns::AsyncPatternsAnalyzer::with_rules(
+                    config.rule_selection("async_patterns"),
+                )
+                .with_targets(&config.targets),
+            ),
+            Box::new(react_hooks::ReactHooksAnalyzer::with_rules(
+                config.rule_selection("react"),
+            )),
+        ]);
+
+        let generated = generated::GeneratedCodeAnalyzer::from_config(config);
+        if generated.is_enabled(config) {
+            dispatcher.analyzers.push(Box::new(generated));
+        }
+
+        // Custom rules run once per prefix; a prefix another source already
+        // claims is skipped (`Registry::from_config` reports it)
+        let mut prefixes: Vec<&str> = config
+            .rules
+            .iter()
+            .map(|r| r.prefix.as_deref().unwrap_or(registry::CUSTOM))
+            .collect();
+        prefixes.sort();
+        prefixes.dedup();
+        for prefix in prefixes {
+            if prefix != registry::CUSTOM {
+                if let Err(e) = registry::check_custom_prefix(prefix) {
+                    eprintln!(
+                        "  warn: skipping custom rules with prefix {:?}: {}",
+                        prefix, e
+                    );
+                    continue;
+                }
+            }
+            let custom = custom_rules::CustomRulesAnalyzer::for_prefix(config, prefix);
+            if custom.is_enabled(config) {
+                dispatcher.analyzers.push(Box::new(custom));
+            }
         }
         dispatcher
     }
@@ -164,38 +634,441 @@ impl AnalyzerDispatcher {
         graph: &CodeGraph,
         config: &RevetConfig,
     ) -> (Vec<Finding>, Vec<AnalyzerTiming>) {
+        let (findings, timings, _) = self.run_graph(graph, config, None);
+        (findings, timings)
+    }
+
+    /// Like `run_graph_analyzers_timed`, with the complexity analyzer in
+    /// delta mode against `base`. Also returns the delta summary, `None`
+    /// when the complexity module is off.
+    pub fn run_graph_analyzers_delta(
+        &self,
+        graph: &CodeGraph,
+        config: &RevetConfig,
+        base: &complexity::delta::DeltaBase,
+    ) -> (
+        Vec<Finding>,
+        Vec<AnalyzerTiming>,
+        Option<complexity::delta::DeltaSummary>,
+    ) {
+        self.run_graph(graph, config, Some(base))
+    }
+
+    fn run_graph(
+        &self,
+        graph: &CodeGraph,
+        config: &RevetConfig,
+        delta: Option<&complexity::delta::DeltaBase>,
+    ) -> (
+        Vec<Finding>,
+        Vec<AnalyzerTiming>,
+        Option<complexity::delta::DeltaSummary>,
+    ) {
         let mut all_findings = Vec::new();
         let mut timings = Vec::new();
+        let mut delta_summary = None;
+        let timed = self.times_files(config);
+        let threshold = config.performance.slow_file_warn();
+        let memo = self.memo.as_ref().map(|memo| {
+            let mut files: Vec<PathBuf> = graph
+                .nodes()
+                .map(|(_, node)| node.file_path().clone())
+                .collect();
+            files.sort();
+            files.dedup();
+            memo.begin(config, &files)
+        });
 
         for analyzer in &self.graph_analyzers {
-            if !analyzer.is_enabled(config) {
+            if !analyzer.is_enabled(config)
+                || !rules::selects_any(analyzer.module(), analyzer.rules(), config)
+            {
                 continue;
             }
 
-            let t = Instant::now();
-            let mut findings = analyzer.analyze_graph(graph, config);
-            let elapsed = t.elapsed();
             let prefix = analyzer.finding_prefix();
+            let (findings, batch) = file_timing::batch(timed, || match delta {
+                Some(base) if prefix == "CMPLX" => {
+                    let (findings, summary) = complexity::delta::analyze(graph, base, config);
+                    delta_summary = Some(summary);
+                    findings
+                }
+                _ => match &memo {
+                    Some(memo) if analyzer.memoizable() => memo
+                        .files(&Self::memo_id(prefix, analyzer.name()), || {
+                            analyzer.analyze_graph(graph, config)
+                        }),
+                    _ => analyzer.analyze_graph(graph, config),
+                },
+            });
+            let findings = Self::finish(prefix, analyzer.module(), findings, config);
 
-            for (i, finding) in findings.iter_mut().enumerate() {
-                finding.id = format!("{}-{:03}", prefix, i + 1);
-            }
+            timings.push(batch.timing(analyzer.name(), prefix, findings.len(), threshold));
+            all_findings.extend(findings);
+        }
 
-            let findings: Vec<Finding> = findings
-                .into_iter()
-                .filter(|f| !config.ignore.findings.contains(&f.id))
-                .collect();
+        (all_findings, timings, delta_summary)
+    }
 
-            timings.push(AnalyzerTiming {
-                name: analyzer.name().to_string(),
-                prefix: prefix.to_string(),
-                duration: elapsed,
-                findings: findings.len(),
+    /// Analyze one in-memory buffer: content analyzers on `content`, plus the
+    /// file-local graph checks on `file`'s nodes in `graph`.
+    ///
+    /// `graph` must already contain the buffer's nodes (parsed from `content`).
+    /// Finding IDs are renumbered per-prefix as in [`run_all`](Self::run_all).
+    pub fn run_buffer(
+        &self,
+        file: &Path,
+        content: &str,
+        graph: Option<&CodeGraph>,
+        repo_root: &Path,
+        config: &RevetConfig,
+    ) -> Vec<Finding> {
+        let content_findings = self
+            .analyzers
+            .iter()
+            .filter(|a| a.is_enabled(config) && rules::selects_any(a.module(), a.rules(), config))
+            .map(|a| {
+                let analyze = || a.analyze_source(file, content, repo_root);
+                let findings = match &self.memo {
+                    Some(memo) if a.per_file() => memo.buffer(
+                        config,
+                        &Self::memo_id(a.finding_prefix(), a.name()),
+                        file,
+                        content,
+                        analyze,
+                    ),
+                    _ => analyze(),
+                };
+                (a.finding_prefix(), a.module(), findings)
             });
-            all_findings.extend(findings);
+        let graph_findings = graph.into_iter().flat_map(|graph| {
+            self.graph_analyzers
+                .iter()
+                .filter(|a| {
+                    a.is_enabled(config) && rules::selects_any(a.module(), a.rules(), config)
+                })
+                .map(move |a| {
+                    (
+                        a.finding_prefix(),
+                        a.module(),
+                        a.analyze_file(graph, file, content, config),
+                    )
+                })
+        });
+
+        let mut all_findings = Vec::new();
+        for (prefix, module, findings) in content_findings.chain(graph_findings) {
+            all_findings.extend(Self::finish(prefix, module, findings, config));
         }
+        all_findings
+    }
 
-        (all_findings, timings)
+    /// Record in `ledger` which content analyzers cover each of `files`:
+    /// the ones that [accept](Analyzer::accepts) it, or that would but are
+    /// disabled. Files that are not UTF-8 text are recorded as unreadable,
+    /// since every analyzer skips them.
+    pub fn record_coverage(
+        &self,
+        files: &[PathBuf],
+        config: &RevetConfig,
+        ledger: &mut CoverageLedger,
+    ) {
+        for file in files {
+            if std::fs::read_to_string(file).is_err() {
+                ledger.unreadable(file);
+                continue;
+            }
+            for analyzer in &self.analyzers {
+                if !analyzer.accepts(file) {
+                    continue;
+                }
+                if analyzer.is_enabled(config) {
+                    ledger.analyzed_by(file, analyzer.finding_prefix());
+                } else {
+                    ledger.disabled_for(file, analyzer.finding_prefix());
+                }
+            }
+        }
+    }
+
+    /// Record in `ledger` the graph analyzers covering each of `files` the
+    /// ledger marks as parsed. Call only when the graph analyzers ran.
+    pub fn record_graph_coverage(
+        &self,
+        files: &[PathBuf],
+        config: &RevetConfig,
+        ledger: &mut CoverageLedger,
+    ) {
+        for file in files {
+            if !ledger.is_parsed(file) {
+                continue;
+            }
+            for analyzer in &self.graph_analyzers {
+                if analyzer.is_enabled(config) {
+                    ledger.analyzed_by(file, analyzer.finding_prefix());
+                } else {
+                    ledger.disabled_for(file, analyzer.finding_prefix());
+                }
+            }
+        }
+    }
+
+    /// `(module, sub-rules)` of every registered analyzer that has a module
+    pub fn rules(&self) -> impl Iterator<Item = (&'static str, &'static [&'static str])> + '_ {
+        let content = self.analyzers.iter().map(|a| (a.module(), a.rules()));
+        let graph = self.graph_analyzers.iter().map(|a| (a.module(), a.rules()));
+        content
+            .chain(graph)
+            .filter_map(|(module, rules)| module.map(|m| (m, rules)))
+    }
+
+    /// The content analyzer reporting under `prefix`
+    pub fn analyzer(&self, prefix: &str) -> Option<&dyn Analyzer> {
+        self.analyzers
+            .iter()
+            .find(|a| a.finding_prefix() == prefix)
+            .map(|a| a.as_ref())
+    }
+
+    /// The graph analyzer reporting under `prefix`
+    pub fn graph_analyzer(&self, prefix: &str) -> Option<&dyn GraphAnalyzer> {
+        self.graph_analyzers
+            .iter()
+            .find(|a| a.finding_prefix() == prefix)
+            .map(|a| a.as_ref())
+    }
+
+    /// Run only the analyzer reporting under `prefix`, whether or not its
+    /// module is enabled: a content analyzer on `files`, a graph analyzer on
+    /// `graph`, keeping its findings in `files`. Sub-rule selection, IDs and
+    /// `[ignore] findings` are applied as in [`run_all`](Self::run_all).
+    ///
+    /// Returns `None` when no analyzer reports under `prefix`, or when it is
+    /// a graph analyzer and there is no `graph`.
+    pub fn run_one(
+        &self,
+        prefix: &str,
+        files: &[PathBuf],
+        graph: Option<&CodeGraph>,
+        repo_root: &Path,
+        config: &RevetConfig,
+    ) -> Option<Vec<Finding>> {
+        if let Some(analyzer) = self.analyzer(prefix) {
+            let findings = analyzer.analyze_files(files, repo_root);
+            return Some(Self::finish(prefix, analyzer.module(), findings, config));
+        }
+        let analyzer = self.graph_analyzer(prefix)?;
+        let findings = analyzer
+            .analyze_graph(graph?, config)
+            .into_iter()
+            .filter(|f| files.contains(&f.file))
+            .collect();
+        Some(Self::finish(prefix, analyzer.module(), findings, config))
+    }
+
+    /// Finding prefixes of every registered analyzer
+    pub fn prefixes(&self) -> impl Iterator<Item = &str> + '_ {
+        let content = self.analyzers.iter().map(|a| a.finding_prefix());
+        let graph = self.graph_analyzers.iter().map(|a| a.finding_prefix());
+        content.chain(graph)
+    }
+
+    /// Audit the `revet-ignore` comments of `files` (`SUPP`), given the
+    /// directive usage of the inline suppression pass. Useless suppressions
+    /// are only reported when `report_useless` is set (full-repo runs).
+    ///
+    /// Returns `(findings, suppressed)`; the latter are audit findings a
+    /// `revet-ignore SUPP` comment covers.
+    pub fn run_suppression_audit(
+        &self,
+        files: &[PathBuf],
+        usage: &DirectiveUsage,
+        report_useless: bool,
+        config: &RevetConfig,
+    ) -> (Vec<Finding>, Vec<SuppressedFinding>) {
+        if !config.modules.suppressions
+            || !rules::selects_any(Some(suppressions::MODULE), suppressions::RULES, config)
+        {
+            return (Vec::new(), Vec::new());
+        }
+        let audit = suppressions::SuppressionAudit::new(self, config);
+        let (findings, silenced) = audit.audit(files, usage, report_useless);
+        let findings = Self::finish("SUPP", Some(suppressions::MODULE), findings, config);
+        let silenced = rules::apply(Some(suppressions::MODULE), silenced, config);
+        (findings, suppressions::silenced(silenced))
+    }
+
+    /// Compare the configuration of a diff's base with the run's (`CONF`).
+    /// The base config selects the sub-rules and severities, so the change
+    /// under review cannot silence its own findings. Returns `None` when the
+    /// module is off on both sides.
+    pub fn run_config_drift(
+        &self,
+        base: &config_drift::ConfigSide,
+        head: &config_drift::ConfigSide,
+        baseline: &Path,
+    ) -> Option<Vec<Finding>> {
+        if !config_drift::enabled(&base.config, &head.config)
+            || !rules::selects_any(
+                Some(config_drift::MODULE),
+                config_drift::RULES,
+                &base.config,
+            )
+        {
+            return None;
+        }
+        let findings = config_drift::compare(base, head, baseline);
+        Some(Self::finish(
+            "CONF",
+            Some(config_drift::MODULE),
+            findings,
+            &base.config,
+        ))
+    }
+
+    /// Check the call sites of the functions a diff changed or renamed
+    /// (`REF`). `head` must cover the whole repository, so that callers in
+    /// untouched files are linked. Returns `None` when the module is off.
+    pub fn run_refactor(
+        &self,
+        head: &CodeGraph,
+        base: &complexity::delta::DeltaBase,
+        config: &RevetConfig,
+    ) -> Option<Vec<Finding>> {
+        if !config.modules.refactor
+            || !rules::selects_any(Some(refactor::MODULE), refactor::RULES, config)
+        {
+            return None;
+        }
+        let findings = refactor::analyze(head, base);
+        Some(Self::finish(
+            "REF",
+            Some(refactor::MODULE),
+            findings,
+            config,
+        ))
+    }
+
+    /// Flag the files an analyzer spent at least `[performance]
+    /// slow_file_warn_ms` on (`PERF`), from the timings of the run.
+    /// Returns `None` when no threshold is set.
+    pub fn run_slow_files<'a>(
+        &self,
+        timings: impl IntoIterator<Item = &'a AnalyzerTiming>,
+        config: &RevetConfig,
+    ) -> Option<Vec<Finding>> {
+        let threshold = config.performance.slow_file_warn()?;
+        let findings = file_timing::slow_files(timings, threshold);
+        Some(Self::finish("PERF", None, findings, config))
+    }
+
+    /// Rank the files of `graph` by git churn and aggregate complexity
+    /// (`HOT`). Returns `None` when the module is off.
+    pub fn run_hotspots(
+        &self,
+        graph: &CodeGraph,
+        repo_root: &Path,
+        config: &RevetConfig,
+    ) -> Option<anyhow::Result<(Vec<Finding>, hotspots::HotspotReport)>> {
+        if !config.modules.hotspots {
+            return None;
+        }
+        Some(
+            hotspots::HotspotReport::build(graph, repo_root, config).map(|report| {
+                if report.churn.partial {
+                    self.git_objects(repo_root, config).narrow(
+                        Feature::RenameTracking,
+                        "contents of other commits are not in the partial clone",
+                    );
+                }
+                let findings = report.findings(repo_root);
+                let findings = Self::finish("HOT", Some(hotspots::MODULE), findings, config);
+                (findings, report)
+            }),
+        )
+    }
+
+    /// Find files no active author maintains among `files`, from git blame
+    /// (`OWN`). Returns `None` when the module is off.
+    pub fn run_ownership(
+        &self,
+        files: &[PathBuf],
+        graph: &CodeGraph,
+        repo_root: &Path,
+        config: &RevetConfig,
+    ) -> Option<anyhow::Result<(Vec<Finding>, ownership::OwnershipReport)>> {
+        if !config.modules.ownership {
+            return None;
+        }
+        let objects = self.git_objects(repo_root, config);
+        Some(
+            ownership::OwnershipReport::build(files, graph, repo_root, config, &objects).map(
+                |report| {
+                    let findings = report.findings(repo_root);
+                    let findings = Self::finish("OWN", Some(ownership::MODULE), findings, config);
+                    (findings, report)
+                },
+            ),
+        )
+    }
+
+    /// Apply the sub-rule selection to one analyzer's findings, renumber
+    /// their IDs per-prefix, drop the ones `[ignore] findings` lists and
+    /// apply `[severity]`
+    fn finish(
+        prefix: &str,
+        module: Option<&str>,
+        findings: Vec<Finding>,
+        config: &RevetConfig,
+    ) -> Vec<Finding> {
+        Self::finish_from(prefix, module, findings, config, &mut 0)
+    }
+
+    /// Like [`finish`](Self::finish), numbering on from `next` (the count
+    /// of findings already numbered), so consecutive slices of one
+    /// analyzer's findings get the IDs of a single call
+    fn finish_from(
+        prefix: &str,
+        module: Option<&str>,
+        findings: Vec<Finding>,
+        config: &RevetConfig,
+        next: &mut usize,
+    ) -> Vec<Finding> {
+        let mut findings = rules::apply(module, findings, config);
+        for finding in findings.iter_mut() {
+            *next += 1;
+            finding.id = format!("{}-{:03}", prefix, next);
+        }
+        findings
+            .into_iter()
+            .filter_map(|mut f| {
+                if config.ignore.findings.contains(&f.id) {
+                    trace::record(&f.file, f.line, Decision::Filtered, || {
+                        format!("{} is listed in [ignore] findings", f.id)
+                    });
+                    return None;
+                }
+                match config.severity_override(&f.id, prefix) {
+                    Some(SeverityOverride::Off) => {
+                        trace::record(&f.file, f.line, Decision::Filtered, || {
+                            format!("{} is turned off in [severity]", f.id)
+                        });
+                        return None;
+                    }
+                    Some(SeverityOverride::Set(severity)) if severity != f.severity => {
+                        trace::record(&f.file, f.line, Decision::Adjusted, || {
+                            format!("{}: {} → {} by [severity]", f.id, f.severity, severity)
+                        });
+                        f.severity = severity;
+                    }
+                    _ => {}
+                }
+                trace::record(&f.file, f.line, Decision::Reported, || {
+                    format!("{}: {}", f.id, f.redacted_message())
+                });
+                Some(f)
+            })
+            .collect()
     }
 
     /// Collect extra file extensions needed by enabled analyzers.
@@ -225,9 +1098,48 @@ impl AnalyzerDispatcher {
         names
     }
 
+    /// Collect dotfile globs needed by enabled analyzers (e.g., `".env*"`).
+    pub fn extra_dotfiles(&self, config: &RevetConfig) -> Vec<&str> {
+        let mut patterns = Vec::new();
+        for analyzer in &self.analyzers {
+            if analyzer.is_enabled(config) {
+                patterns.extend_from_slice(analyzer.extra_dotfiles());
+            }
+        }
+        patterns.sort();
+        patterns.dedup();
+        patterns
+    }
+
+    /// Metrics behind the thresholds of every analyzer, for `revet
+    /// calibrate`: content analyzers measure `files`, graph analyzers
+    /// `graph` when one was built. Modules that are off are measured too,
+    /// so a threshold can be chosen before turning one on. No findings are
+    /// produced.
+    pub fn measure(
+        &self,
+        files: &[PathBuf],
+        graph: Option<&CodeGraph>,
+        repo_root: &Path,
+        config: &RevetConfig,
+    ) -> Vec<Metric> {
+        let mut metrics: Vec<Metric> = self
+            .analyzers
+            .iter()
+            .flat_map(|analyzer| analyzer.measure(files, repo_root))
+            .collect();
+        if let Some(graph) = graph {
+            for analyzer in &self.graph_analyzers {
+                metrics.extend(analyzer.measure(graph, config));
+            }
+        }
+        metrics
+    }
+
     /// Run all enabled analyzers and return combined findings
     ///
-    /// Finding IDs are renumbered per-prefix to ensure sequential ordering
+    /// Findings of sub-rules deselected in `config` are dropped, then IDs
+    /// are renumbered per-prefix to ensure sequential ordering
     /// (e.g., SEC-001, SEC-002, ...).
     pub fn run_all(
         &self,
@@ -238,25 +1150,19 @@ impl AnalyzerDispatcher {
         let mut all_findings = Vec::new();
 
         for analyzer in &self.analyzers {
-            if !analyzer.is_enabled(config) {
+            if !analyzer.is_enabled(config)
+                || !rules::selects_any(analyzer.module(), analyzer.rules(), config)
+            {
                 continue;
             }
 
-            let mut findings = analyzer.analyze_files(files, repo_root);
-            let prefix = analyzer.finding_prefix();
-
-            // Renumber finding IDs sequentially
-            for (i, finding) in findings.iter_mut().enumerate() {
-                finding.id = format!("{}-{:03}", prefix, i + 1);
-            }
-
-            // Filter out suppressed findings
-            let findings: Vec<Finding> = findings
-                .into_iter()
-                .filter(|f| !config.ignore.findings.contains(&f.id))
-                .collect();
-
-            all_findings.extend(findings);
+            let findings = analyzer.analyze_files(files, repo_root);
+            all_findings.extend(Self::finish(
+                analyzer.finding_prefix(),
+                analyzer.module(),
+                findings,
+                config,
+            ));
         }
 
         all_findings
@@ -282,52 +1188,278 @@ impl AnalyzerDispatcher {
         repo_root: &Path,
         config: &RevetConfig,
     ) -> (Vec<Finding>, Vec<AnalyzerTiming>) {
-        // Collect enabled analyzers
-        let enabled: Vec<&dyn Analyzer> = self
-            .analyzers
-            .iter()
-            .filter(|a| a.is_enabled(config))
-            .map(|a| &**a)
-            .collect();
+        match self.run_parallel(self.enabled(config), files, repo_root, config, None) {
+            Ok(results) => results,
+            Err(Cancelled) => unreachable!("no cancel token"),
+        }
+    }
+
+    /// Like `run_all_parallel`, but stops with [`Cancelled`] when `token` is
+    /// cancelled. The token is checked before each analyzer starts; one
+    /// that is already running finishes first.
+    pub fn run_all_parallel_cancellable(
+        &self,
+        files: &[PathBuf],
+        repo_root: &Path,
+        config: &RevetConfig,
+        token: &CancelToken,
+    ) -> Result<Vec<Finding>, Cancelled> {
+        self.run_parallel(self.enabled(config), files, repo_root, config, Some(token))
+            .map(|(findings, _)| findings)
+    }
 
+    fn run_parallel(
+        &self,
+        enabled: Vec<&dyn Analyzer>,
+        files: &[PathBuf],
+        repo_root: &Path,
+        config: &RevetConfig,
+        token: Option<&CancelToken>,
+    ) -> Result<(Vec<Finding>, Vec<AnalyzerTiming>), Cancelled> {
         // Run all analyzers in parallel, capturing timing per analyzer
-        let per_analyzer: Vec<(String, String, Vec<Finding>, Duration)> = enabled
+        let timed = self.times_files(config);
+        let memo = self.memo_run(files, config);
+        let per_analyzer = enabled
             .par_iter()
             .map(|analyzer| {
-                let t = Instant::now();
-                let findings = analyzer.analyze_files(files, repo_root);
-                let elapsed = t.elapsed();
-                (
-                    analyzer.name().to_string(),
-                    analyzer.finding_prefix().to_string(),
-                    findings,
-                    elapsed,
-                )
+                if token.is_some_and(|t| t.is_cancelled()) {
+                    return None;
+                }
+                let (findings, batch) =
+                    Self::invoke(*analyzer, files, repo_root, timed, memo.as_ref());
+                Some((*analyzer, findings, batch))
             })
-            .collect();
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Cancelled)?;
+        if let Some(token) = token {
+            token.check()?;
+        }
 
-        // Sequential post-processing: renumber, filter, collect timings
+        // Sequential post-processing: select, renumber, filter, collect timings
+        let threshold = config.performance.slow_file_warn();
         let mut all_findings = Vec::new();
         let mut timings = Vec::new();
-        for (name, prefix, mut findings, duration) in per_analyzer {
-            for (i, finding) in findings.iter_mut().enumerate() {
-                finding.id = format!("{}-{:03}", prefix, i + 1);
-            }
-            let findings: Vec<Finding> = findings
-                .into_iter()
-                .filter(|f| !config.ignore.findings.contains(&f.id))
-                .collect();
-            timings.push(AnalyzerTiming {
-                name,
-                prefix,
-                duration,
-                findings: findings.len(),
-            });
+        for (analyzer, findings, batch) in per_analyzer {
+            let prefix = analyzer.finding_prefix();
+            let findings = Self::finish(prefix, analyzer.module(), findings, config);
+            timings.push(batch.timing(analyzer.name(), prefix, findings.len(), threshold));
             all_findings.extend(findings);
         }
 
+        Ok((all_findings, timings))
+    }
+
+    /// Run `analyzer` on `files` as one batch. With `timed` or a `memo`, a
+    /// per-file analyzer gets one file at a time so that each call can be
+    /// clocked, and skipped when the memo holds its findings.
+    fn invoke(
+        analyzer: &dyn Analyzer,
+        files: &[PathBuf],
+        repo_root: &Path,
+        timed: bool,
+        memo: Option<&MemoRun>,
+    ) -> (Vec<Finding>, file_timing::Batch) {
+        file_timing::batch(timed, || {
+            if !analyzer.per_file() || !(timed || memo.is_some()) {
+                return analyzer.analyze_files(files, repo_root);
+            }
+            let id = Self::memo_id(analyzer.finding_prefix(), analyzer.name());
+            files
+                .iter()
+                .flat_map(|file| {
+                    let analyze = || {
+                        file_timing::time_file(file, || {
+                            analyzer.analyze_files(std::slice::from_ref(file), repo_root)
+                        })
+                    };
+                    match memo {
+                        Some(memo) => memo.file(&id, file, analyze),
+                        None => analyze(),
+                    }
+                })
+                .collect()
+        })
+    }
+
+    /// Key of an analyzer's entries in the memo
+    fn memo_id(prefix: &str, name: &str) -> String {
+        format!("{prefix}/{name}")
+    }
+
+    /// Like `run_all_parallel_timed`, with the per-file analyzers run one
+    /// partition of `files` at a time. `on_partition` gets each partition's
+    /// findings, with their final IDs, as soon as every per-file analyzer is
+    /// done with it; the other analyzers run alongside on all files.
+    ///
+    /// `partitions` must cover `files` in order (see [`partition::partition`]);
+    /// the returned findings are then the same as `run_all_parallel_timed`'s.
+    pub fn run_all_partitioned_timed<F>(
+        &self,
+        files: &[PathBuf],
+        partitions: &[partition::Partition],
+        repo_root: &Path,
+        config: &RevetConfig,
+        mut on_partition: F,
+    ) -> (Vec<Finding>, Vec<AnalyzerTiming>)
+    where
+        F: FnMut(&partition::Partition, Vec<Finding>) + Send,
+    {
+        let enabled = self.enabled(config);
+        let (local, whole): (Vec<usize>, Vec<usize>) =
+            (0..enabled.len()).partition(|&i| enabled[i].per_file());
+        let timed = self.times_files(config);
+        let memo = self.memo_run(files, config);
+        let run = |i: usize, files: &[PathBuf]| {
+            let (findings, batch) =
+                Self::invoke(enabled[i], files, repo_root, timed, memo.as_ref());
+            (i, findings, batch)
+        };
+
+        let (whole_results, mut results) = rayon::join(
+            || whole.par_iter().map(|&i| run(i, files)).collect::<Vec<_>>(),
+            || {
+                let mut results: Vec<(Vec<Finding>, file_timing::Batch)> =
+                    vec![(Vec::new(), file_timing::Batch::default()); enabled.len()];
+                let mut numbered = vec![0; enabled.len()];
+                for partition in partitions {
+                    let slice = &files[partition.files.clone()];
+                    let per_analyzer: Vec<_> = local.par_iter().map(|&i| run(i, slice)).collect();
+                    let mut partition_findings = Vec::new();
+                    for (i, findings, batch) in per_analyzer {
+                        let analyzer = enabled[i];
+                        let findings = Self::finish_from(
+                            analyzer.finding_prefix(),
+                            analyzer.module(),
+                            findings,
+                            config,
+                            &mut numbered[i],
+                        );
+                        partition_findings.extend(findings.iter().cloned());
+                        results[i].0.extend(findings);
+                        results[i].1.extend(batch);
+                    }
+                    on_partition(partition, partition_findings);
+                }
+                results
+            },
+        );
+        for (i, findings, batch) in whole_results {
+            let analyzer = enabled[i];
+            let findings = Self::finish(
+                analyzer.finding_prefix(),
+                analyzer.module(),
+                findings,
+                config,
+            );
+            results[i] = (findings, batch);
+        }
+
+        // Same order as `run_all_parallel_timed`: by analyzer
+        let threshold = config.performance.slow_file_warn();
+        let mut all_findings = Vec::new();
+        let mut timings = Vec::new();
+        for (analyzer, (findings, batch)) in enabled.iter().zip(results) {
+            timings.push(batch.timing(
+                analyzer.name(),
+                analyzer.finding_prefix(),
+                findings.len(),
+                threshold,
+            ));
+            all_findings.extend(findings);
+        }
         (all_findings, timings)
     }
+
+    /// Names of the enabled analyzers whose findings need the whole
+    /// repository: domain analyzers that are not per-file and, when
+    /// `graph` is set, the graph analyzers
+    pub fn whole_repo_analyzers(&self, config: &RevetConfig, graph: bool) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .enabled(config)
+            .into_iter()
+            .filter(|a| !a.per_file())
+            .map(|a| a.name())
+            .collect();
+        if graph {
+            names.extend(
+                self.graph_analyzers
+                    .iter()
+                    .filter(|a| {
+                        a.is_enabled(config) && rules::selects_any(a.module(), a.rules(), config)
+                    })
+                    .map(|a| a.name()),
+            );
+        }
+        names
+    }
+
+    /// `[modules]` names of the enabled content analyzers, in registration
+    /// order
+    pub fn modules(&self, config: &RevetConfig) -> Vec<&'static str> {
+        let mut modules = Vec::new();
+        for module in self.enabled(config).iter().filter_map(|a| a.module()) {
+            if !modules.contains(&module) {
+                modules.push(module);
+            }
+        }
+        modules
+    }
+
+    /// `[modules]` names of the enabled graph analyzers, in registration
+    /// order
+    pub fn graph_modules(&self, config: &RevetConfig) -> Vec<&'static str> {
+        let mut modules = Vec::new();
+        for analyzer in &self.graph_analyzers {
+            if !analyzer.is_enabled(config)
+                || !rules::selects_any(analyzer.module(), analyzer.rules(), config)
+            {
+                continue;
+            }
+            if let Some(module) = analyzer.module().filter(|m| !modules.contains(m)) {
+                modules.push(module);
+            }
+        }
+        modules
+    }
+
+    /// `[modules]` name of the content or graph analyzer reporting under
+    /// `prefix`
+    pub fn module_of(&self, prefix: &str) -> Option<&'static str> {
+        match self.analyzer(prefix) {
+            Some(analyzer) => analyzer.module(),
+            None => self.graph_analyzer(prefix)?.module(),
+        }
+    }
+
+    /// Like `run_all_parallel_timed`, limited to the enabled content
+    /// analyzers of `modules`
+    pub fn run_modules_timed(
+        &self,
+        modules: &[&str],
+        files: &[PathBuf],
+        repo_root: &Path,
+        config: &RevetConfig,
+    ) -> (Vec<Finding>, Vec<AnalyzerTiming>) {
+        let enabled = self
+            .enabled(config)
+            .into_iter()
+            .filter(|a| a.module().is_some_and(|m| modules.contains(&m)))
+            .collect();
+        match self.run_parallel(enabled, files, repo_root, config, None) {
+            Ok(results) => results,
+            Err(Cancelled) => unreachable!("no cancel token"),
+        }
+    }
+
+    /// Enabled analyzers with at least one selected sub-rule, in
+    /// registration order
+    fn enabled(&self, config: &RevetConfig) -> Vec<&dyn Analyzer> {
+        self.analyzers
+            .iter()
+            .filter(|a| a.is_enabled(config) && rules::selects_any(a.module(), a.rules(), config))
+            .map(|a| &**a)
+            .collect()
+    }
 }
 
 impl Default for AnalyzerDispatcher {
@@ -357,3 +1489,22 @@ pub(crate) fn make_finding(
         ..Default::default()
     }
 }
+
+/// Like [`make_finding`], with the message and suggestion taken from the
+/// message catalog so outputs can render them in the user's locale
+pub(crate) fn catalog_finding(
+    severity: Severity,
+    message: Msg,
+    file: PathBuf,
+    line: usize,
+    suggestion: Option<Msg>,
+    fix_kind: Option<FixKind>,
+) -> Finding {
+    let english = message.english();
+    let english_suggestion = suggestion.as_ref().map(Msg::english);
+    Finding {
+        message_ref: Some(message),
+        suggestion_ref: suggestion,
+        ..make_finding(severity, english, file, line, english_suggestion, fix_kind)
+    }
+}
@@ -0,0 +1,308 @@
+//! Numeric-precision analyzer — floats where exact numbers are needed
+//!
+//! Sub-rules:
+//!
+//! - `money_float`: variables, parameters, fields and properties whose name
+//!   ends in a money word (`price`, `unitPrice`, `total_amount`, `fees`)
+//!   declared as binary floats in Python, JavaScript/TypeScript, Java, Go,
+//!   Rust or C#. The suggestion names the language's decimal type or
+//!   integer minor units (see [`floats`])
+//! - `float_equality`: `==`/`!=` where one side is a float literal or a name
+//!   declared as a float. Medium confidence: declarations are matched by
+//!   name within the file, not by scope
+//! - `unsafe_integer_id`: ID-named values in JavaScript/TypeScript given a
+//!   literal longer than a number keeps, or taken from `JSON.parse` (see
+//!   [`ids`]). A BigInt literal is only suggested when the file's
+//!   ECMAScript target is ES2020 or later (see [`crate::language_target`])
+//!
+//! Names are matched on their trailing words, so `money_names = ["price"]`
+//! covers `price`, `unit_price`, `UnitPrice` and `prices` but not
+//! `price_ratio`; an entry of several words (`"line_total"`) must end the
+//! name. Test code, as the [`CodeKinds`] of `[files]` classify it, is
+//! skipped unless `include_tests` is set.
+//!
+//! Each sub-rule's severity comes from `[analyzers.numeric.severity]`
+//! (defaults: warning, info, warning).
+//!
+//! Finding prefix: `NUM-`
+
+pub mod floats;
+pub mod ids;
+
+use crate::analyzer::magic_numbers::tunables::name_words;
+use crate::analyzer::{catalog_finding, Analyzer};
+use crate::code_kind::{CodeKind, CodeKinds};
+use crate::config::{FilesConfig, NumericConfig, RevetConfig};
+use crate::finding::{Confidence, Finding, Severity};
+use crate::language_target::{Language, LanguageTargets};
+use crate::msg;
+use crate::parser::grammar::Family;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Module name of the numeric-precision analyzer
+pub const MODULE: &str = "numeric";
+
+/// Sub-rules of the numeric-precision analyzer
+pub const RULES: &[&str] = &["money_float", "float_equality", "unsafe_integer_id"];
+
+/// A list of names from the config, matched on trailing words
+#[derive(Debug, Clone, Default)]
+pub struct Names(Vec<Vec<String>>);
+
+impl Names {
+    pub fn new(names: &[String]) -> Self {
+        Self(
+            names
+                .iter()
+                .map(|n| name_words(n))
+                .filter(|w| !w.is_empty())
+                .collect(),
+        )
+    }
+
+    /// Whether `name` ends in the words of an entry; the last word may be
+    /// plural (`prices`, `taxes`, `ids`)
+    pub fn matches(&self, name: &str) -> bool {
+        let words = name_words(name.trim_start_matches(['$', '@', '#']));
+        self.0.iter().any(|entry| {
+            let Some(start) = words.len().checked_sub(entry.len()) else {
+                return false;
+            };
+            let tail = &words[start..];
+            let last = entry.len() - 1;
+            tail[..last] == entry[..last] && {
+                let (word, wanted) = (tail[last].as_str(), entry[last].as_str());
+                word == wanted
+                    || word
+                        .strip_prefix(wanted)
+                        .is_some_and(|rest| matches!(rest, "s" | "es"))
+            }
+        })
+    }
+}
+
+/// Analyzer that reports floats used for money, exact float comparisons
+/// and integer IDs JavaScript cannot hold
+pub struct NumericAnalyzer {
+    code_kinds: CodeKinds,
+    /// `[targets]`
+    targets: BTreeMap<String, String>,
+    money_names: Names,
+    id_names: Names,
+    include_tests: bool,
+    money_float: Severity,
+    float_equality: Severity,
+    unsafe_integer_id: Severity,
+}
+
+impl NumericAnalyzer {
+    /// Create a numeric-precision analyzer with the default settings
+    pub fn new() -> Self {
+        Self::with_config(&NumericConfig::default())
+    }
+
+    /// Create a numeric-precision analyzer honouring `[analyzers.numeric]`
+    pub fn with_config(config: &NumericConfig) -> Self {
+        let severity =
+            |rule: &str, default: Severity| match config.severity.get(rule).map(String::as_str) {
+                Some("error") => Severity::Error,
+                Some("warning") => Severity::Warning,
+                Some("info") => Severity::Info,
+                _ => default,
+            };
+        Self {
+            code_kinds: CodeKinds::default(),
+            targets: BTreeMap::new(),
+            money_names: Names::new(&config.money_names),
+            id_names: Names::new(&config.id_names),
+            include_tests: config.include_tests,
+            money_float: severity("money_float", Severity::Warning),
+            float_equality: severity("float_equality", Severity::Info),
+            unsafe_integer_id: severity("unsafe_integer_id", Severity::Warning),
+        }
+    }
+
+    /// Tell test code apart with the `test_paths`/`script_paths` of `[files]`
+    pub fn with_files(mut self, files: &FilesConfig) -> Self {
+        self.code_kinds = CodeKinds::from_config(files);
+        self
+    }
+
+    /// Take the language targets `[targets]` sets over manifests
+    pub fn with_targets(mut self, targets: &BTreeMap<String, String>) -> Self {
+        self.targets = targets.clone();
+        self
+    }
+
+    fn scan_source(
+        &self,
+        path: &Path,
+        content: &str,
+        repo_root: &Path,
+        targets: &mut LanguageTargets,
+    ) -> Vec<Finding> {
+        let Some(grammar) = floats::grammar(path) else {
+            return Vec::new();
+        };
+        if !self.include_tests
+            && self.code_kinds.classify(path, repo_root, content) == CodeKind::Test
+        {
+            return Vec::new();
+        }
+        let Some(tree) = grammar.parse(content) else {
+            return Vec::new();
+        };
+        let root = tree.root_node();
+        let family = grammar.family;
+
+        let found = floats::scan(family, root, content, &self.money_names);
+        let mut findings: Vec<Finding> = found
+            .money
+            .iter()
+            .map(|money| Finding {
+                rule: Some("money_float".to_string()),
+                ..catalog_finding(
+                    self.money_float,
+                    msg!("numeric-money-float", name = money.name.as_str()),
+                    path.to_path_buf(),
+                    money.line,
+                    money_suggestion(family),
+                    None,
+                )
+            })
+            .collect();
+        findings.extend(found.comparisons.iter().map(|comparison| Finding {
+            rule: Some("float_equality".to_string()),
+            confidence: Confidence::Medium,
+            ..catalog_finding(
+                self.float_equality,
+                msg!(
+                    "numeric-float-equality",
+                    expression = comparison.expression.as_str()
+                ),
+                path.to_path_buf(),
+                comparison.line,
+                Some(msg!("numeric-float-equality.suggestion")),
+                None,
+            )
+        }));
+        if family == Family::Js {
+            let ids = ids::scan(root, content, &self.id_names);
+            if !ids.is_empty() {
+                let bigint = targets
+                    .for_file(Language::EcmaScript, path)
+                    .at_least("2020");
+                findings.extend(ids.into_iter().map(|id| self.unsafe_id(path, id, bigint)));
+            }
+        }
+        findings.sort_by_key(|f| f.line);
+        findings
+    }
+
+    /// `bigint`: whether the target has BigInt literals (ES2020)
+    fn unsafe_id(&self, path: &Path, id: ids::UnsafeId, bigint: bool) -> Finding {
+        let (message, suggestion, confidence) = match &id.literal {
+            Some(literal) => (
+                msg!(
+                    "numeric-unsafe-id-literal",
+                    name = id.name.as_str(),
+                    literal = literal.as_str()
+                ),
+                if bigint {
+                    msg!(
+                        "numeric-unsafe-id-literal.suggestion",
+                        literal = literal.as_str()
+                    )
+                } else {
+                    msg!("numeric-unsafe-id-literal.no-bigint")
+                },
+                Confidence::High,
+            ),
+            None => (
+                msg!("numeric-unsafe-id-parsed", name = id.name.as_str()),
+                msg!("numeric-unsafe-id-parsed.suggestion"),
+                Confidence::Medium,
+            ),
+        };
+        Finding {
+            rule: Some("unsafe_integer_id".to_string()),
+            confidence,
+            ..catalog_finding(
+                self.unsafe_integer_id,
+                message,
+                path.to_path_buf(),
+                id.line,
+                Some(suggestion),
+                None,
+            )
+        }
+    }
+}
+
+/// What to hold money in, per language
+fn money_suggestion(family: Family) -> Option<crate::i18n::Msg> {
+    Some(match family {
+        Family::Python => msg!("numeric-money-float.python"),
+        Family::Js => msg!("numeric-money-float.javascript"),
+        Family::Java => msg!("numeric-money-float.java"),
+        Family::Go => msg!("numeric-money-float.go"),
+        Family::Rust => msg!("numeric-money-float.rust"),
+        Family::CSharp => msg!("numeric-money-float.csharp"),
+        _ => return None,
+    })
+}
+
+impl Default for NumericAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer for NumericAnalyzer {
+    fn name(&self) -> &str {
+        "Numeric precision"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "NUM"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.numeric
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some(MODULE)
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        floats::grammar(path).is_some()
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], repo_root: &Path) -> Vec<Finding> {
+        let mut targets = LanguageTargets::new(repo_root, &self.targets);
+        files
+            .iter()
+            .filter(|f| self.accepts(f))
+            .filter_map(|f| {
+                let content = std::fs::read_to_string(f).ok()?;
+                Some(self.scan_source(f, &content, repo_root, &mut targets))
+            })
+            .flatten()
+            .collect()
+    }
+
+    fn analyze_source(&self, path: &Path, content: &str, repo_root: &Path) -> Vec<Finding> {
+        let mut targets = LanguageTargets::new(repo_root, &self.targets);
+        self.scan_source(path, content, repo_root, &mut targets)
+    }
+}
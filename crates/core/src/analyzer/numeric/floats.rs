@@ -0,0 +1,315 @@
+//! Floating-point money and exact float comparisons
+//!
+//! Declarations are found with tree-sitter in Python, JavaScript/TypeScript,
+//! Java, Go, Rust and C#. A declaration is a float when its type is one
+//! (`float`, `double`/`Double`, `float64`, `f64`, `float?`) or, without a
+//! type, when its value is a float literal or a conversion (`float(x)`,
+//! `parseFloat(x)`, `float64(x)`, `x as f64`, `(double) x`). TypeScript's
+//! `number` is not a float type by itself: only the value decides there.
+//!
+//! - Variables, parameters, fields and properties declared as floats are
+//!   returned when their name matches the money names
+//! - `==`/`!=` (`===`/`!==`) comparisons are returned when one side is a
+//!   float literal or a name declared as a float anywhere in the file
+
+use super::Names;
+use crate::parser::grammar::{language_for, Family, Grammar};
+use std::collections::HashSet;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// The grammar of `path`, if it is scanned
+pub fn grammar(path: &Path) -> Option<Grammar> {
+    language_for(path).filter(|g| {
+        matches!(
+            g.family,
+            Family::Python | Family::Js | Family::Java | Family::Go | Family::Rust | Family::CSharp
+        )
+    })
+}
+
+/// A money name declared as a float
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneyFloat {
+    pub name: String,
+    pub line: usize,
+}
+
+/// An exact comparison involving a float
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    /// The comparison as written, on one line
+    pub expression: String,
+    pub line: usize,
+}
+
+/// What one file holds
+#[derive(Debug, Default)]
+pub struct Floats {
+    pub money: Vec<MoneyFloat>,
+    pub comparisons: Vec<Comparison>,
+}
+
+/// Money floats and float comparisons under `root`, in source order
+pub fn scan(family: Family, root: Node, source: &str, money: &Names) -> Floats {
+    let scanner = Scanner { family, source };
+    let mut declarations = Vec::new();
+    scanner.declarations(root, &mut declarations);
+
+    let mut floats = Floats::default();
+    let mut names = HashSet::new();
+    for declaration in declarations.iter().filter(|d| scanner.is_float(d)) {
+        let name = scanner.text(declaration.name);
+        names.insert(name);
+        if money.matches(name) {
+            floats.money.push(MoneyFloat {
+                name: name.to_string(),
+                line: declaration.name.start_position().row + 1,
+            });
+        }
+    }
+    scanner.comparisons(root, &names, &mut floats.comparisons);
+    floats.money.dedup();
+    floats
+}
+
+/// A declared name, with its type and initial value when written
+struct Declaration<'t> {
+    name: Node<'t>,
+    ty: Option<Node<'t>>,
+    value: Option<Node<'t>>,
+}
+
+struct Scanner<'a> {
+    family: Family,
+    source: &'a str,
+}
+
+impl<'a> Scanner<'a> {
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.source.as_bytes()).unwrap_or("")
+    }
+
+    fn declarations<'t>(&self, node: Node<'t>, out: &mut Vec<Declaration<'t>>) {
+        self.declared(node, out);
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.declarations(child, out);
+        }
+    }
+
+    /// The declarations `node` makes itself
+    fn declared<'t>(&self, node: Node<'t>, out: &mut Vec<Declaration<'t>>) {
+        let field = |name: &str| node.child_by_field_name(name);
+        let mut push = |name: Option<Node<'t>>, ty: Option<Node<'t>>, value: Option<Node<'t>>| {
+            let name = name.map(|n| match n.kind() {
+                // `self.balance: float = 0`
+                "attribute" => n.child_by_field_name("attribute").unwrap_or(n),
+                _ => n,
+            });
+            if let Some(name) = name.filter(|n| is_name(n.kind())) {
+                out.push(Declaration { name, ty, value });
+            }
+        };
+        match (self.family, node.kind()) {
+            (Family::Python, "assignment") => push(field("left"), field("type"), field("right")),
+            (Family::Python, "typed_parameter") => push(node.named_child(0), field("type"), None),
+            (Family::Python, "typed_default_parameter") => {
+                push(field("name"), field("type"), field("value"))
+            }
+            (Family::Python, "default_parameter") => push(field("name"), None, field("value")),
+            (Family::Js, "variable_declarator") => push(field("name"), None, field("value")),
+            (Family::Js, "public_field_definition" | "field_definition") => push(
+                field("name").or_else(|| field("property")),
+                None,
+                field("value"),
+            ),
+            (Family::Js, "assignment_pattern") => push(field("left"), None, field("right")),
+            (Family::Js, "required_parameter" | "optional_parameter") => {
+                push(field("pattern"), None, field("value"))
+            }
+            (Family::Java, "field_declaration" | "local_variable_declaration") => {
+                let mut cursor = node.walk();
+                for declarator in node.children_by_field_name("declarator", &mut cursor) {
+                    push(
+                        declarator.child_by_field_name("name"),
+                        field("type"),
+                        declarator.child_by_field_name("value"),
+                    );
+                }
+            }
+            (Family::Java, "formal_parameter") => push(field("name"), field("type"), None),
+            (Family::Go, "var_spec" | "parameter_declaration" | "field_declaration") => {
+                let values = field("value").map(|list| named_children(list));
+                let mut cursor = node.walk();
+                for (i, name) in node.children_by_field_name("name", &mut cursor).enumerate() {
+                    let value = values.as_ref().and_then(|v| v.get(i).copied());
+                    push(Some(name), field("type"), value);
+                }
+            }
+            (Family::Go, "short_var_declaration") => {
+                let (Some(left), Some(right)) = (field("left"), field("right")) else {
+                    return;
+                };
+                let values = named_children(right);
+                for (i, name) in named_children(left).into_iter().enumerate() {
+                    push(Some(name), None, values.get(i).copied());
+                }
+            }
+            (Family::Rust, "let_declaration") => {
+                push(field("pattern"), field("type"), field("value"))
+            }
+            (Family::Rust, "parameter") => push(field("pattern"), field("type"), None),
+            (Family::Rust, "field_declaration") => push(field("name"), field("type"), None),
+            (Family::CSharp, "variable_declaration") => {
+                for declarator in named_children(node)
+                    .into_iter()
+                    .filter(|c| c.kind() == "variable_declarator")
+                {
+                    let name = declarator.child_by_field_name("name");
+                    // The initializer is the declarator's other named child
+                    let value = named_children(declarator)
+                        .into_iter()
+                        .find(|c| Some(*c) != name)
+                        .map(|v| match v.kind() {
+                            "equals_value_clause" => v.named_child(0).unwrap_or(v),
+                            _ => v,
+                        });
+                    push(name, field("type"), value);
+                }
+            }
+            (Family::CSharp, "parameter" | "property_declaration") => {
+                push(field("name"), field("type"), field("value"))
+            }
+            _ => {}
+        }
+    }
+
+    fn is_float(&self, declaration: &Declaration) -> bool {
+        match declaration.ty.map(|t| self.text(t).trim()) {
+            // `var`/`let` in Java and C# infer the type from the value
+            Some(ty) if ty != "var" => self.is_float_type(ty),
+            _ => declaration.value.is_some_and(|v| self.is_float_value(v)),
+        }
+    }
+
+    fn is_float_type(&self, ty: &str) -> bool {
+        let ty = ty.trim_end_matches('?');
+        match self.family {
+            Family::Python => matches!(
+                ty,
+                "float" | "Optional[float]" | "float | None" | "None | float"
+            ),
+            Family::Java => matches!(ty, "double" | "float" | "Double" | "Float"),
+            Family::Go => matches!(ty, "float64" | "float32"),
+            Family::Rust => matches!(ty, "f64" | "f32"),
+            Family::CSharp => matches!(
+                ty,
+                "double" | "float" | "Double" | "Single" | "System.Double" | "System.Single"
+            ),
+            _ => false,
+        }
+    }
+
+    /// A float literal, possibly negated or parenthesized
+    fn is_float_literal(&self, node: Node) -> bool {
+        let text = self.text(node).to_ascii_lowercase();
+        match node.kind() {
+            "float" | "float_literal" => true,
+            "decimal_floating_point_literal" | "hex_floating_point_literal" => true,
+            // `1.5m` is a C# decimal
+            "real_literal" => !text.ends_with('m'),
+            "number" if self.family == Family::Js => {
+                !text.starts_with("0x")
+                    && !text.starts_with("0b")
+                    && !text.starts_with("0o")
+                    && !text.ends_with('n')
+                    && (text.contains('.') || text.contains('e'))
+            }
+            kind if kind.contains("unary") || kind.starts_with("parenthesized") => {
+                node.named_child_count() == 1
+                    && node
+                        .named_child(0)
+                        .is_some_and(|c| self.is_float_literal(c))
+            }
+            _ => false,
+        }
+    }
+
+    /// A float literal or a conversion to float
+    fn is_float_value(&self, node: Node) -> bool {
+        if self.is_float_literal(node) {
+            return true;
+        }
+        let text = |field: &str| node.child_by_field_name(field).map(|n| self.text(n));
+        match node.kind() {
+            "call" | "call_expression" => matches!(
+                text("function"),
+                Some("float" | "parseFloat" | "Number.parseFloat" | "float64" | "float32")
+            ),
+            "type_cast_expression" | "cast_expression" => {
+                text("type").is_some_and(|ty| ty != "var" && self.is_float_type(ty))
+            }
+            _ => false,
+        }
+    }
+
+    fn comparisons(&self, node: Node, floats: &HashSet<&str>, out: &mut Vec<Comparison>) {
+        if let Some((left, right)) = self.equality(node) {
+            let float = |side: Node| {
+                self.is_float_literal(side) || {
+                    let text = self.text(side);
+                    let path = text
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '$'));
+                    path && text.rsplit('.').next().is_some_and(|n| floats.contains(n))
+                }
+            };
+            if float(left) || float(right) {
+                out.push(Comparison {
+                    expression: self
+                        .text(node)
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    line: node.start_position().row + 1,
+                });
+            }
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.comparisons(child, floats, out);
+        }
+    }
+
+    /// Both sides of an `==`/`!=` comparison
+    fn equality<'t>(&self, node: Node<'t>) -> Option<(Node<'t>, Node<'t>)> {
+        if !matches!(node.kind(), "binary_expression" | "comparison_operator") {
+            return None;
+        }
+        let mut cursor = node.walk();
+        let operator = node
+            .children(&mut cursor)
+            .find(|c| !c.is_named() && !matches!(self.text(*c), "(" | ")"))?;
+        if !matches!(self.text(operator), "==" | "!=" | "===" | "!==") {
+            return None;
+        }
+        match named_children(node)[..] {
+            [left, right] => Some((left, right)),
+            _ => None,
+        }
+    }
+}
+
+/// Node kinds that are a plain declared name
+fn is_name(kind: &str) -> bool {
+    matches!(
+        kind,
+        "identifier" | "property_identifier" | "private_property_identifier" | "field_identifier"
+    )
+}
+
+fn named_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).collect()
+}
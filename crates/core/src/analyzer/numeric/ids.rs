@@ -0,0 +1,169 @@
+//! Integer IDs beyond what a JavaScript number holds
+//!
+//! A JavaScript number keeps 15–17 significant digits; 64-bit IDs (Twitter
+//! snowflakes, database keys) silently round. In JavaScript and TypeScript,
+//! an ID-named variable, field or object key is returned when it is given
+//!
+//! - a numeric literal with more than [`MAX_DIGITS`] significant digits
+//!   (high confidence), or
+//! - the result of `JSON.parse(...)`, directly, through a property
+//!   (`JSON.parse(body).userId`) or by destructuring (`const { id } =
+//!   JSON.parse(body)`): numbers in the payload have already been rounded
+//!   (medium confidence, as the payload may well send strings)
+
+use super::Names;
+use tree_sitter::Node;
+
+/// Significant digits a JavaScript number always keeps
+pub const MAX_DIGITS: usize = 15;
+
+/// An ID that may have lost digits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsafeId {
+    pub name: String,
+    pub line: usize,
+    /// The literal, or `None` for a value from `JSON.parse`
+    pub literal: Option<String>,
+}
+
+/// Unsafe IDs under `root`, in source order
+pub fn scan(root: Node, source: &str, ids: &Names) -> Vec<UnsafeId> {
+    let scanner = Scanner { source, ids };
+    let mut found = Vec::new();
+    scanner.walk(root, &mut found);
+    found
+}
+
+struct Scanner<'a> {
+    source: &'a str,
+    ids: &'a Names,
+}
+
+impl<'a> Scanner<'a> {
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.source.as_bytes()).unwrap_or("")
+    }
+
+    fn walk(&self, node: Node, out: &mut Vec<UnsafeId>) {
+        let field = |name: &str| node.child_by_field_name(name);
+        match node.kind() {
+            "variable_declarator" => {
+                if let (Some(name), Some(value)) = (field("name"), field("value")) {
+                    if name.kind() != "object_pattern" {
+                        self.assigned(name, value, out);
+                    } else if self.is_parsed(value) {
+                        self.destructured(name, out);
+                    }
+                }
+            }
+            "assignment_expression" => {
+                if let (Some(left), Some(right)) = (field("left"), field("right")) {
+                    let name = match left.kind() {
+                        "member_expression" => left.child_by_field_name("property"),
+                        _ => Some(left),
+                    };
+                    if let Some(name) = name {
+                        self.assigned(name, right, out);
+                    }
+                }
+            }
+            "pair" => {
+                if let (Some(key), Some(value)) = (field("key"), field("value")) {
+                    if self.is_long_literal(value) {
+                        self.assigned(key, value, out);
+                    }
+                }
+            }
+            "public_field_definition" | "field_definition" => {
+                if let (Some(name), Some(value)) =
+                    (field("name").or_else(|| field("property")), field("value"))
+                {
+                    self.assigned(name, value, out);
+                }
+            }
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.walk(child, out);
+        }
+    }
+
+    /// `name = value`, when `name` is an ID
+    fn assigned(&self, name: Node, value: Node, out: &mut Vec<UnsafeId>) {
+        let text = self.text(name).trim_matches(['"', '\'']);
+        if !self.ids.matches(text) {
+            return;
+        }
+        let literal = if self.is_long_literal(value) {
+            Some(self.text(value).to_string())
+        } else if self.is_parsed(value) {
+            None
+        } else {
+            return;
+        };
+        out.push(UnsafeId {
+            name: text.to_string(),
+            line: name.start_position().row + 1,
+            literal,
+        });
+    }
+
+    /// ID names pulled out of a parsed payload: `{ id, user: { userId } }`
+    fn destructured(&self, pattern: Node, out: &mut Vec<UnsafeId>) {
+        let mut cursor = pattern.walk();
+        for child in pattern.named_children(&mut cursor) {
+            let name = match child.kind() {
+                "shorthand_property_identifier_pattern" => Some(child),
+                "pair_pattern" => match child.child_by_field_name("value") {
+                    Some(value) if value.kind() == "object_pattern" => {
+                        self.destructured(value, out);
+                        None
+                    }
+                    _ => child.child_by_field_name("key"),
+                },
+                "object_assignment_pattern" => child.child_by_field_name("left"),
+                _ => None,
+            };
+            let Some(name) = name else { continue };
+            let text = self.text(name);
+            if self.ids.matches(text) {
+                out.push(UnsafeId {
+                    name: text.to_string(),
+                    line: name.start_position().row + 1,
+                    literal: None,
+                });
+            }
+        }
+    }
+
+    /// A decimal integer literal with more than [`MAX_DIGITS`] significant
+    /// digits; BigInt literals (`123n`) are exact
+    fn is_long_literal(&self, node: Node) -> bool {
+        if node.kind() != "number" {
+            return false;
+        }
+        let text = self.text(node);
+        if !text.bytes().all(|b| b.is_ascii_digit() || b == b'_') {
+            return false;
+        }
+        let digits: String = text.chars().filter(char::is_ascii_digit).collect();
+        digits.trim_matches('0').len() > MAX_DIGITS
+    }
+
+    /// `JSON.parse(...)`, or a property of it
+    fn is_parsed(&self, node: Node) -> bool {
+        match node.kind() {
+            "call_expression" => node
+                .child_by_field_name("function")
+                .is_some_and(|f| self.text(f) == "JSON.parse"),
+            "member_expression" | "subscript_expression" => node
+                .child_by_field_name("object")
+                .is_some_and(|o| self.is_parsed(o)),
+            "await_expression" | "parenthesized_expression" | "as_expression" => {
+                node.named_child(0).is_some_and(|c| self.is_parsed(c))
+            }
+            _ => false,
+        }
+    }
+}
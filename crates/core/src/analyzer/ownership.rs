@@ -0,0 +1,340 @@
+//! Ownership analyzer (`OWN`) — code nobody maintains anymore
+//!
+//! Every analyzed file is blamed once at HEAD (see [`Authorship`]; at most
+//! `[analyzers.ownership] max_files` of them, cached per commit). Authors
+//! are active when they appear in the roster (`active` and `roster_file`)
+//! or, without one, when they authored a commit inside `window`. A file —
+//! or, with `rollup = "directory"`, all files directly in a directory — is
+//! orphaned when no active author last changed at least `min_share` percent
+//! of its lines.
+//!
+//! Orphans are reported as info, or as warnings when their aggregate
+//! complexity reaches `complexity` or they sit under a `[risk]
+//! critical_paths` glob. Paths with owners in `CODEOWNERS` are skipped:
+//! their ownership is assigned formally (see [`codeowners`]). The whole list
+//! of orphans, most lines first, is kept for `revet stats`. With
+//! `hash_authors`, authors appear as `author-<hash>` everywhere.
+
+pub mod codeowners;
+
+use super::catalog_finding;
+use super::complexity::file_complexity;
+use crate::authorship::{Authorship, FileAuthors};
+use crate::churn::Window;
+use crate::config::RevetConfig;
+use crate::finding::{Finding, Severity};
+use crate::git_objects::GitObjects;
+use crate::graph::CodeGraph;
+use crate::msg;
+use crate::storage::StoragePaths;
+use anyhow::{anyhow, Context, Result};
+use codeowners::CodeOwners;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// `[modules]` name of the analyzer
+pub const MODULE: &str = "ownership";
+
+const DAY_SECS: i64 = 24 * 3600;
+
+/// A file or directory no active author maintains
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Orphan {
+    /// Path relative to the repository root, `/`-separated (`.` for the
+    /// root directory)
+    pub path: String,
+    /// Lines blamed
+    pub lines: usize,
+    /// Percent of lines last changed by authors who are not active
+    pub departed: u8,
+    /// Authors of the majority of lines, most lines first
+    pub owners: Vec<String>,
+    /// Days from the last change to the HEAD commit
+    pub idle_days: u64,
+    /// Summed cyclomatic complexity of the functions
+    pub complexity: usize,
+    /// Complex or on a critical path (reported as a warning)
+    pub escalated: bool,
+}
+
+/// Result of one ownership pass
+#[derive(Debug, Clone)]
+pub struct OwnershipReport {
+    /// Active authors: the roster entries, or the window's committers
+    pub active: usize,
+    /// Files blamed
+    pub blamed: usize,
+    /// Files left out by `max_files`
+    pub skipped: usize,
+    /// Files skipped because `CODEOWNERS` assigns them
+    pub codeowned: usize,
+    /// The repository is a shallow clone, so authorship may be incomplete
+    pub shallow: bool,
+    /// Orphaned files or directories, most lines first
+    pub ranking: Vec<Orphan>,
+}
+
+/// Who counts as active: a roster, or the committers of the window
+#[derive(Debug, Clone)]
+pub enum Active {
+    Roster(Vec<String>),
+    Committers(BTreeSet<String>),
+}
+
+impl Active {
+    /// Whether the author with `email` is active. Roster entries match the
+    /// whole email, a prefix ending in `@`, or the part before the `@`.
+    pub fn contains(&self, email: &str) -> bool {
+        match self {
+            Active::Committers(emails) => emails.contains(email),
+            Active::Roster(entries) => entries.iter().any(|entry| {
+                email == entry
+                    || (entry.ends_with('@') && email.starts_with(entry.as_str()))
+                    || (!entry.contains('@') && email.split('@').next() == Some(entry.as_str()))
+            }),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Active::Roster(entries) => entries.len(),
+            Active::Committers(emails) => emails.len(),
+        }
+    }
+}
+
+impl OwnershipReport {
+    /// Find the orphaned `files`, blamed through `objects`. Fails when
+    /// `repo_root` has no git history, the window is invalid or the roster
+    /// file cannot be read.
+    pub fn build(
+        files: &[PathBuf],
+        graph: &CodeGraph,
+        repo_root: &Path,
+        config: &RevetConfig,
+        objects: &GitObjects,
+    ) -> Result<Self> {
+        let settings = &config.analyzers.ownership;
+        let window = Window::parse(&settings.window)
+            .ok_or_else(|| anyhow!("invalid ownership window {:?}", settings.window))?;
+
+        let mut rels: Vec<String> = files.iter().map(|f| relative(f, repo_root)).collect();
+        rels.sort();
+        rels.dedup();
+        let skipped = rels.len().saturating_sub(settings.max_files);
+        rels.truncate(settings.max_files);
+
+        let paths = StoragePaths::resolve(repo_root, config);
+        let authorship = Authorship::load_or_compute(repo_root, &paths, &rels, window, objects)?;
+
+        let mut roster: Vec<String> = settings
+            .active
+            .iter()
+            .map(|a| a.trim().to_lowercase())
+            .collect();
+        if let Some(file) = &settings.roster_file {
+            let content = std::fs::read_to_string(repo_root.join(file))
+                .with_context(|| format!("Failed to read roster_file {}", file))?;
+            roster.extend(
+                content
+                    .lines()
+                    .map(|line| line.split('#').next().unwrap_or("").trim().to_lowercase())
+                    .filter(|line| !line.is_empty()),
+            );
+        }
+        let active = if roster.is_empty() {
+            Active::Committers(authorship.active.clone())
+        } else {
+            Active::Roster(roster)
+        };
+
+        let codeowners = CodeOwners::load(repo_root);
+        let complexity: BTreeMap<String, usize> = file_complexity(graph, config)
+            .into_iter()
+            .map(|(path, complexity)| (relative(&path, repo_root), complexity))
+            .collect();
+        let critical: Vec<glob::Pattern> = config
+            .risk
+            .critical_paths
+            .iter()
+            .filter_map(|g| glob::Pattern::new(g).ok())
+            .collect();
+
+        // Blamed files, rolled up when asked to
+        let mut units: BTreeMap<String, Unit> = BTreeMap::new();
+        let mut blamed = 0;
+        let mut codeowned = 0;
+        for rel in &rels {
+            let Some(authors) = authorship.files.get(rel) else {
+                continue;
+            };
+            blamed += 1;
+            if codeowners.owns(rel) {
+                codeowned += 1;
+                continue;
+            }
+            let key = match settings.rollup.as_str() {
+                "directory" => match rel.rsplit_once('/') {
+                    Some((dir, _)) => dir.to_string(),
+                    None => ".".to_string(),
+                },
+                _ => rel.clone(),
+            };
+            let unit = units.entry(key).or_default();
+            unit.add(authors);
+            unit.complexity += complexity.get(rel).copied().unwrap_or(0);
+            unit.critical |= critical.iter().any(|g| g.matches(rel));
+        }
+
+        let mut ranking: Vec<Orphan> = units
+            .into_iter()
+            .filter_map(|(path, unit)| {
+                let total = unit.authors.total();
+                if total == 0 || total < settings.min_lines {
+                    return None;
+                }
+                let best_active = unit
+                    .authors
+                    .lines
+                    .iter()
+                    .filter(|(email, _)| active.contains(email))
+                    .map(|(_, lines)| *lines)
+                    .max()
+                    .unwrap_or(0);
+                if best_active * 100 >= usize::from(settings.min_share) * total {
+                    return None;
+                }
+                let active_lines: usize = unit
+                    .authors
+                    .lines
+                    .iter()
+                    .filter(|(email, _)| active.contains(email))
+                    .map(|(_, lines)| lines)
+                    .sum();
+                let departed = ((total - active_lines) * 100 + total / 2) / total;
+                Some(Orphan {
+                    owners: majority(&unit.authors)
+                        .into_iter()
+                        .map(|email| display(email, settings.hash_authors))
+                        .collect(),
+                    idle_days: ((authorship.head_time - unit.authors.last_change).max(0) / DAY_SECS)
+                        as u64,
+                    escalated: unit.critical || unit.complexity >= settings.complexity.max(1),
+                    complexity: unit.complexity,
+                    departed: departed as u8,
+                    lines: total,
+                    path,
+                })
+            })
+            .collect();
+        ranking.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.path.cmp(&b.path)));
+
+        Ok(Self {
+            active: active.len(),
+            blamed,
+            skipped,
+            codeowned,
+            shallow: authorship.shallow,
+            ranking,
+        })
+    }
+
+    /// An info finding for every orphan, a warning for escalated ones
+    pub fn findings(&self, repo_root: &Path) -> Vec<Finding> {
+        self.ranking
+            .iter()
+            .map(|orphan| {
+                let owners = orphan.owners.join(", ");
+                let message = if orphan.idle_days >= 60 {
+                    msg!(
+                        "ownership-orphaned-months",
+                        departed = orphan.departed,
+                        owners = owners.as_str(),
+                        months = orphan.idle_days / 30
+                    )
+                } else {
+                    msg!(
+                        "ownership-orphaned-days",
+                        departed = orphan.departed,
+                        owners = owners.as_str(),
+                        days = orphan.idle_days
+                    )
+                };
+                let severity = if orphan.escalated {
+                    Severity::Warning
+                } else {
+                    Severity::Info
+                };
+                let path = match orphan.path.as_str() {
+                    "." => repo_root.to_path_buf(),
+                    path => repo_root.join(path),
+                };
+                catalog_finding(
+                    severity,
+                    message,
+                    path,
+                    1,
+                    Some(msg!("ownership-orphaned-days.suggestion")),
+                    None,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Blame results of one file or directory
+#[derive(Debug, Default)]
+struct Unit {
+    authors: FileAuthors,
+    complexity: usize,
+    critical: bool,
+}
+
+impl Unit {
+    fn add(&mut self, file: &FileAuthors) {
+        for (email, lines) in &file.lines {
+            *self.authors.lines.entry(email.clone()).or_default() += lines;
+        }
+        self.authors.last_change = self.authors.last_change.max(file.last_change);
+    }
+}
+
+/// Fewest authors who together last changed more than half of the lines,
+/// most lines first
+fn majority(authors: &FileAuthors) -> Vec<&str> {
+    let mut by_lines: Vec<(&str, usize)> = authors
+        .lines
+        .iter()
+        .map(|(email, lines)| (email.as_str(), *lines))
+        .collect();
+    by_lines.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let total = authors.total();
+    let mut covered = 0;
+    let mut owners = Vec::new();
+    for (email, lines) in by_lines {
+        if covered * 2 > total {
+            break;
+        }
+        covered += lines;
+        owners.push(email);
+    }
+    owners
+}
+
+/// How an author is shown: the email, or `author-<hash>` with
+/// `hash_authors`
+pub fn display(email: &str, hash: bool) -> String {
+    if !hash {
+        return email.to_string();
+    }
+    let digest = Sha256::digest(email.as_bytes());
+    let short: String = digest[..4].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("author-{}", short)
+}
+
+fn relative(path: &Path, repo_root: &Path) -> String {
+    let rel = path.strip_prefix(repo_root).unwrap_or(path);
+    rel.to_string_lossy().replace('\\', "/")
+}
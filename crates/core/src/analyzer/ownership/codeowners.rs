@@ -0,0 +1,98 @@
+//! GitHub `CODEOWNERS` files
+//!
+//! The first of `.github/CODEOWNERS`, `CODEOWNERS` and `docs/CODEOWNERS`
+//! is read, as GitHub does. Patterns follow gitignore rules: a pattern with
+//! a leading or inner `/` is anchored at the root, one without matches at
+//! any depth, a trailing `/` matches only directory contents, `*` stays
+//! within one path segment and `**` spans several. The last matching line
+//! wins, and a line without owners leaves its paths unowned.
+
+use glob::{MatchOptions, Pattern};
+use std::path::Path;
+
+const LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+const OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// One `CODEOWNERS` line
+#[derive(Debug, Clone)]
+struct Rule {
+    patterns: Vec<Pattern>,
+    owners: Vec<String>,
+}
+
+/// The rules of a repository's `CODEOWNERS`
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+}
+
+impl CodeOwners {
+    /// Read the `CODEOWNERS` of `repo_root`; empty when there is none
+    pub fn load(repo_root: &Path) -> Self {
+        LOCATIONS
+            .iter()
+            .find_map(|location| std::fs::read_to_string(repo_root.join(location)).ok())
+            .map(|content| Self::parse(&content))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .filter_map(|line| {
+                let line = line.split(" #").next()?.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let mut fields = line.split_whitespace();
+                let patterns = globs(fields.next()?);
+                Some(Rule {
+                    owners: fields.map(str::to_string).collect(),
+                    patterns,
+                })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Whether `path` (relative to the root, `/`-separated) has owners
+    pub fn owns(&self, path: &str) -> bool {
+        !self.owners(path).is_empty()
+    }
+
+    /// The owners of `path` (`@user`, `@org/team` or an email), as listed
+    pub fn owners(&self, path: &str) -> &[String] {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.patterns.iter().any(|p| p.matches_with(path, OPTIONS)))
+            .map_or(&[], |rule| &rule.owners)
+    }
+
+    /// Whether the file has no rules at all
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// The globs a `CODEOWNERS` pattern stands for
+fn globs(pattern: &str) -> Vec<Pattern> {
+    let directory = pattern.ends_with('/');
+    let body = pattern.trim_start_matches('/').trim_end_matches('/');
+    let anchored = pattern.starts_with('/') || body.contains('/');
+    let base = if anchored {
+        body.to_string()
+    } else {
+        format!("**/{}", body)
+    };
+    let mut globs = vec![format!("{}/**", base)];
+    if !directory {
+        globs.push(base);
+    }
+    globs.iter().filter_map(|g| Pattern::new(g).ok()).collect()
+}
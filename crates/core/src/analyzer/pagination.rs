@@ -0,0 +1,165 @@
+//! Pagination analyzer — API handlers returning whole tables
+//!
+//! HTTP handlers in Python (FastAPI, Flask, Django REST framework) and
+//! JavaScript/TypeScript (Express, Fastify) are reported when they run a
+//! query reading a whole collection and return its result, directly or
+//! after serializing it, without taking a page: no `limit`/`offset`/
+//! `cursor`/`page` parameter and no slicing of the result. See [`handlers`]
+//! for what counts as a handler and a collection read.
+//!
+//! A handler passes when
+//!
+//! - it carries a `revet: unbounded-ok` comment, on the line above it or
+//!   anywhere inside it, for endpoints meant to return everything
+//! - the model or table it reads is listed in `small_tables` of
+//!   `[analyzers.pagination]` (`countries`, `Currency`)
+//!
+//! The suggestion names the pagination idiom of the ORM or framework that
+//! matched: DRF pagination classes, SQLAlchemy `.limit()`, Prisma
+//! `take`/`skip`, Fastify pagination plugins.
+//!
+//! Finding prefix: `PAGE-`
+
+pub mod handlers;
+
+use crate::analyzer::{catalog_finding, Analyzer};
+use crate::config::{PaginationConfig, RevetConfig};
+use crate::finding::{Confidence, Finding, Severity};
+use crate::i18n::Msg;
+use crate::msg;
+use handlers::{Framework, Orm, Unbounded};
+use std::path::{Path, PathBuf};
+
+/// Module name of the pagination analyzer
+pub const MODULE: &str = "pagination";
+
+/// Comment marking a handler that returns everything on purpose
+pub const UNBOUNDED_OK: &str = "revet: unbounded-ok";
+
+/// Analyzer that reports HTTP handlers returning unbounded query results
+pub struct PaginationAnalyzer {
+    /// `small_tables`, lower-cased
+    small_tables: Vec<String>,
+}
+
+impl PaginationAnalyzer {
+    /// Create a pagination analyzer with the default settings
+    pub fn new() -> Self {
+        Self::with_config(&PaginationConfig::default())
+    }
+
+    /// Create a pagination analyzer honouring `[analyzers.pagination]`
+    pub fn with_config(config: &PaginationConfig) -> Self {
+        Self {
+            small_tables: config
+                .small_tables
+                .iter()
+                .map(|t| t.trim().to_lowercase())
+                .collect(),
+        }
+    }
+
+    fn scan_source(&self, path: &Path, content: &str) -> Vec<Finding> {
+        let Some(grammar) = handlers::grammar(path) else {
+            return Vec::new();
+        };
+        let Some(tree) = grammar.parse(content) else {
+            return Vec::new();
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        handlers::scan(grammar.family, tree.root_node(), content)
+            .into_iter()
+            .filter(|u| !self.allowed(u, &lines))
+            .map(|u| Finding {
+                confidence: Confidence::Medium,
+                ..catalog_finding(
+                    Severity::Warning,
+                    msg!(
+                        "pagination-unbounded",
+                        handler = u.handler.as_str(),
+                        query = u.query.callee.as_str()
+                    ),
+                    path.to_path_buf(),
+                    u.line,
+                    Some(suggestion(&u)),
+                    None,
+                )
+            })
+            .collect()
+    }
+
+    /// Whether a handler is marked `revet: unbounded-ok` or reads a small
+    /// table
+    fn allowed(&self, unbounded: &Unbounded, lines: &[&str]) -> bool {
+        let (first, last) = unbounded.lines;
+        let marked = lines
+            .iter()
+            .take(last)
+            .skip(first.saturating_sub(2))
+            .any(|l| l.contains(UNBOUNDED_OK));
+        let small = unbounded.query.table.as_deref().is_some_and(|table| {
+            let table = table.trim_matches('"').to_lowercase();
+            let name = table.rsplit('.').next().unwrap_or(&table);
+            self.small_tables.iter().any(|t| t == name)
+        });
+        marked || small
+    }
+}
+
+/// The pagination idiom of what matched
+fn suggestion(unbounded: &Unbounded) -> Msg {
+    match (unbounded.query.orm, unbounded.framework) {
+        (Orm::Prisma, _) => msg!("pagination-unbounded.prisma"),
+        (_, Framework::Fastify) => msg!("pagination-unbounded.fastify"),
+        (Orm::Django, _) => msg!("pagination-unbounded.django"),
+        (Orm::SqlAlchemy, _) => msg!("pagination-unbounded.sqlalchemy"),
+        (Orm::Sequelize, _) => msg!("pagination-unbounded.sequelize"),
+        (Orm::Mongo, _) => msg!("pagination-unbounded.mongo"),
+        (Orm::Sql, _) => msg!("pagination-unbounded.sql"),
+    }
+}
+
+impl Default for PaginationAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer for PaginationAnalyzer {
+    fn name(&self) -> &str {
+        "Pagination"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "PAGE"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.pagination
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some(MODULE)
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        handlers::grammar(path).is_some()
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
+        files
+            .iter()
+            .filter(|f| self.accepts(f))
+            .filter_map(|f| Some(self.scan_source(f, &std::fs::read_to_string(f).ok()?)))
+            .flatten()
+            .collect()
+    }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        self.scan_source(path, content)
+    }
+}
@@ -0,0 +1,735 @@
+//! HTTP handlers returning whole collections
+//!
+//! Handlers are found with tree-sitter:
+//!
+//! - Python: functions decorated with a route (`@app.get("/users")`,
+//!   `@router.post(...)`, `@bp.route(...)`, `@api_view([...])`), and the
+//!   `get`/`list` methods of classes deriving from a `...View`/`...ViewSet`
+//! - JavaScript/TypeScript: the last function passed to a route
+//!   registration whose first argument is a path (`app.get("/users", ...)`,
+//!   `router.post(...)`, `fastify.get(...)`)
+//!
+//! Within a handler, a query reads a whole collection when its method chain
+//! ends in a collection read and holds nothing that bounds it:
+//!
+//! | ORM | Collection reads | Bounded by |
+//! |-----|------------------|------------|
+//! | Django | `Model.objects.all()`, `.filter()`, `.exclude()`, `.order_by()` | slicing |
+//! | SQLAlchemy | `.all()` | `.limit()`, `.paginate()`, `.first()`, slicing |
+//! | Prisma | `findMany(...)` | a `take` argument |
+//! | Sequelize | `findAll(...)` | a `limit` argument |
+//! | MongoDB / Mongoose | `Model.find(...)`, `.toArray()` | `.limit()` |
+//! | SQL | `execute`/`query`/`raw`/... of a `SELECT` literal, `.fetchall()` after one | `LIMIT`, `TOP`, `FETCH FIRST`, `OFFSET` |
+//!
+//! The result is followed through assignments: a name holding it, or a
+//! value computed from it without slicing (`[UserOut.from_orm(u) for u in
+//! users]`, `list(users)`), is tainted until reassigned. A handler returns
+//! the collection when a `return` (or, in JavaScript, `res.json(...)`,
+//! `res.send(...)`, `reply.send(...)`) mentions a tainted name or a
+//! collection read outside a subscript.
+
+use crate::analyzer::magic_numbers::tunables::name_words;
+use crate::parser::grammar::{language_for, Family, Grammar};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+use tree_sitter::Node;
+
+/// The grammar of `path`, if it is scanned
+pub fn grammar(path: &Path) -> Option<Grammar> {
+    language_for(path).filter(|g| matches!(g.family, Family::Python | Family::Js))
+}
+
+/// Where a collection read comes from; picks the suggestion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orm {
+    Django,
+    SqlAlchemy,
+    Prisma,
+    Sequelize,
+    Mongo,
+    Sql,
+}
+
+/// The web framework a handler is registered with, where it matters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framework {
+    Fastify,
+    Other,
+}
+
+/// A query reading a whole collection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub orm: Orm,
+    /// The call as the message shows it: `prisma.user.findMany`
+    pub callee: String,
+    /// Model or table read, when it can be told
+    pub table: Option<String>,
+}
+
+/// A handler returning a whole collection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unbounded {
+    /// `list_users`, or `GET /users` in JavaScript
+    pub handler: String,
+    pub framework: Framework,
+    pub query: Query,
+    /// Line of the `return` (or response call)
+    pub line: usize,
+    /// Lines the handler spans, decorators included
+    pub lines: (usize, usize),
+}
+
+/// Words of parameter names that ask for one page: `limit`, `page_size`,
+/// `perPage`, `cursor`, `offset`, `skip`, `take`
+const PAGE_WORDS: &[&str] = &["limit", "offset", "cursor", "page", "skip", "take"];
+
+/// Methods that bound a query or read one row
+const BOUNDING: &[&str] = &[
+    "limit",
+    "offset",
+    "paginate",
+    "slice",
+    "first",
+    "one",
+    "one_or_none",
+    "scalar",
+    "scalar_one",
+    "get",
+    "get_or_404",
+    "first_or_404",
+    "count",
+    "exists",
+    "aggregate",
+    "take",
+    "skip",
+    "findFirst",
+    "findUnique",
+    "findOne",
+    "findById",
+    "countDocuments",
+    "[]",
+];
+
+/// Functions computing one value from a collection: `len(users)`
+const SCALARS: &[&str] = &["len", "sum", "min", "max", "any", "all", "bool"];
+
+/// Django queryset methods that still read every matching row
+const DJANGO_READS: &[&str] = &[
+    "all",
+    "filter",
+    "exclude",
+    "order_by",
+    "values",
+    "values_list",
+];
+
+/// Methods running a SQL string
+const SQL_CALLS: &[&str] = &[
+    "execute",
+    "query",
+    "raw",
+    "fetch",
+    "fetch_all",
+    "many",
+    "any",
+    "manyOrNone",
+];
+
+/// Handlers under `root` that return a whole collection, in source order
+pub fn scan(family: Family, root: Node, source: &str) -> Vec<Unbounded> {
+    let scanner = Scanner { family, source };
+    let mut handlers = Vec::new();
+    scanner.handlers(root, &mut handlers);
+    handlers
+        .into_iter()
+        .filter(|h| !scanner.paginated(h))
+        .filter_map(|h| scanner.unbounded(&h))
+        .collect()
+}
+
+/// A handler found in the tree
+struct Handler<'t> {
+    name: String,
+    framework: Framework,
+    /// The whole definition, decorators or registration call included
+    outer: Node<'t>,
+    function: Node<'t>,
+}
+
+/// One link of a method chain: `.filter(...)` is `filter` with arguments
+struct Link<'t> {
+    name: &'t str,
+    arguments: Option<Node<'t>>,
+}
+
+struct Scanner<'a> {
+    family: Family,
+    source: &'a str,
+}
+
+impl<'a> Scanner<'a> {
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.source.as_bytes()).unwrap_or("")
+    }
+
+    fn handlers<'t>(&self, node: Node<'t>, out: &mut Vec<Handler<'t>>) {
+        let found = match self.family {
+            Family::Python => self.python_handler(node),
+            _ => self.js_handler(node),
+        };
+        if let Some(handler) = found {
+            out.push(handler);
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.handlers(child, out);
+        }
+    }
+
+    /// `@app.get("/users") def list_users(...)`, or `get`/`list` of a view
+    fn python_handler<'t>(&self, node: Node<'t>) -> Option<Handler<'t>> {
+        let function = match node.kind() {
+            "decorated_definition" => node
+                .child_by_field_name("definition")
+                .filter(|d| d.kind() == "function_definition")?,
+            "function_definition"
+                if node
+                    .parent()
+                    .is_none_or(|p| p.kind() != "decorated_definition") =>
+            {
+                node
+            }
+            _ => return None,
+        };
+        let name = self.text(function.child_by_field_name("name")?);
+        let mut cursor = node.walk();
+        let routed = node
+            .named_children(&mut cursor)
+            .filter(|c| c.kind() == "decorator")
+            .any(|d| {
+                let text = self.text(d).trim_start_matches('@');
+                let callee = text.split('(').next().unwrap_or(text).trim();
+                let last = callee.rsplit('.').next().unwrap_or(callee);
+                last == "api_view"
+                    || (callee.contains('.')
+                        && matches!(
+                            last,
+                            "get" | "post" | "put" | "patch" | "delete" | "route" | "api_route"
+                        ))
+            });
+        (routed || self.view_method(node, name)).then(|| Handler {
+            name: name.to_string(),
+            framework: Framework::Other,
+            outer: node,
+            function,
+        })
+    }
+
+    /// Whether `definition` is `get` or `list` of a class deriving from a
+    /// view or viewset
+    fn view_method(&self, definition: Node, name: &str) -> bool {
+        if !matches!(name, "get" | "list") {
+            return false;
+        }
+        let class = definition
+            .parent()
+            .filter(|p| p.kind() == "block")
+            .and_then(|b| b.parent())
+            .filter(|c| c.kind() == "class_definition");
+        class
+            .and_then(|c| c.child_by_field_name("superclasses"))
+            .is_some_and(|bases| {
+                let bases = self.text(bases);
+                bases.contains("View") || bases.contains("ViewSet")
+            })
+    }
+
+    /// `app.get("/users", async (req, res) => { ... })`
+    fn js_handler<'t>(&self, node: Node<'t>) -> Option<Handler<'t>> {
+        if node.kind() != "call_expression" {
+            return None;
+        }
+        let callee = node
+            .child_by_field_name("function")
+            .filter(|f| f.kind() == "member_expression")?;
+        let method = self.text(callee.child_by_field_name("property")?);
+        if !matches!(method, "get" | "post" | "put" | "patch" | "delete" | "all") {
+            return None;
+        }
+        let arguments = node.child_by_field_name("arguments")?;
+        let mut cursor = arguments.walk();
+        let args: Vec<Node> = arguments.named_children(&mut cursor).collect();
+        let path = args
+            .first()
+            .filter(|a| matches!(a.kind(), "string" | "template_string"))
+            .map(|a| self.text(*a).trim_matches(['"', '\'', '`']))
+            .filter(|p| p.starts_with('/'))?;
+        let function = args
+            .iter()
+            .rev()
+            .find(|a| is_js_function(a.kind()))
+            .copied()?;
+        let object = callee
+            .child_by_field_name("object")
+            .map(|o| self.text(o))
+            .unwrap_or("");
+        let replies = function
+            .child_by_field_name("parameters")
+            .is_some_and(|p| self.text(p).contains("reply"));
+        let framework = if object.contains("fastify") || replies {
+            Framework::Fastify
+        } else {
+            Framework::Other
+        };
+        Some(Handler {
+            name: format!("{} {}", method.to_uppercase(), path),
+            framework,
+            outer: node,
+            function,
+        })
+    }
+
+    /// Whether the handler takes a page: a parameter named like one in
+    /// Python, a name read anywhere in the handler in JavaScript (where the
+    /// page comes from `req.query`)
+    fn paginated(&self, handler: &Handler) -> bool {
+        let page = |name: &str| {
+            name_words(name)
+                .iter()
+                .any(|w| PAGE_WORDS.contains(&w.to_lowercase().as_str()))
+        };
+        match self.family {
+            Family::Python => {
+                let Some(parameters) = handler.function.child_by_field_name("parameters") else {
+                    return false;
+                };
+                let mut cursor = parameters.walk();
+                let names: Vec<&str> = parameters
+                    .named_children(&mut cursor)
+                    .filter_map(|p| match p.kind() {
+                        "identifier" => Some(self.text(p)),
+                        "typed_parameter" => p.named_child(0).map(|n| self.text(n)),
+                        _ => p.child_by_field_name("name").map(|n| self.text(n)),
+                    })
+                    .collect();
+                names.into_iter().any(page)
+            }
+            _ => {
+                let mut names = Vec::new();
+                self.js_names(handler.function, &mut names);
+                names.into_iter().any(page)
+            }
+        }
+    }
+
+    /// Names read in `node` outside query arguments
+    fn js_names(&self, node: Node, out: &mut Vec<&'a str>) {
+        if matches!(
+            node.kind(),
+            "property_identifier" | "shorthand_property_identifier_pattern"
+        ) {
+            out.push(self.text(node));
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.js_names(child, out);
+        }
+    }
+
+    /// The first collection the handler returns
+    fn unbounded(&self, handler: &Handler) -> Option<Unbounded> {
+        let body = handler.function.child_by_field_name("body")?;
+        let mut flow = Flow {
+            tainted: HashMap::new(),
+            sql: self.executed_sql(body),
+        };
+        // `async () => prisma.user.findMany()` returns its body
+        let (query, line) = match body.kind() {
+            "statement_block" | "block" => self.returned(body, &mut flow)?,
+            _ => (self.collection(body, &flow)?, body.start_position().row + 1),
+        };
+        Some(Unbounded {
+            handler: handler.name.clone(),
+            framework: handler.framework,
+            query,
+            line,
+            lines: (
+                handler.outer.start_position().row + 1,
+                handler.outer.end_position().row + 1,
+            ),
+        })
+    }
+
+    /// An unbounded `SELECT` run anywhere in `node`, for a later `fetchall()`
+    fn executed_sql(&self, node: Node) -> Option<Query> {
+        if let Some(query) = self.sql_call(node) {
+            return Some(query);
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.children(&mut cursor).collect();
+        children.into_iter().find_map(|c| self.executed_sql(c))
+    }
+
+    /// Walks `node` in source order, following assignments, until a return
+    /// hands back a collection
+    fn returned(&self, node: Node, flow: &mut Flow) -> Option<(Query, usize)> {
+        // Callbacks within the handler (`users.map(u => ...)`) return
+        // to their caller, not from the handler
+        if is_js_function(node.kind()) || node.kind() == "lambda" {
+            return None;
+        }
+        match node.kind() {
+            "assignment" | "assignment_expression" => {
+                self.assign(node, "left", "right", flow);
+            }
+            "variable_declarator" => {
+                self.assign(node, "name", "value", flow);
+            }
+            "return_statement" => {
+                let value = node.named_child(0)?;
+                if let Some(query) = self.collection(value, flow) {
+                    return Some((query, node.start_position().row + 1));
+                }
+            }
+            "call_expression" => {
+                if let Some(query) = self.response(node, flow) {
+                    return Some((query, node.start_position().row + 1));
+                }
+            }
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.children(&mut cursor).collect();
+        children.into_iter().find_map(|c| self.returned(c, flow))
+    }
+
+    /// `res.json(users)`, `res.status(200).send(users)`, `reply.send(users)`
+    fn response(&self, call: Node, flow: &Flow) -> Option<Query> {
+        let links = self.chain(call);
+        let last = links.last()?;
+        if !matches!(last.name, "json" | "send" | "jsonp") {
+            return None;
+        }
+        let value = last.arguments?.named_child(0)?;
+        self.collection(value, flow)
+    }
+
+    /// Taint (or clear) the name assigned in `node`
+    fn assign(&self, node: Node, left: &str, right: &str, flow: &mut Flow) {
+        let (Some(name), Some(value)) = (
+            node.child_by_field_name(left),
+            node.child_by_field_name(right),
+        ) else {
+            return;
+        };
+        if name.kind() != "identifier" {
+            return;
+        }
+        let name = self.text(name).to_string();
+        match self.collection(value, flow) {
+            Some(query) => {
+                flow.tainted.insert(name, query);
+            }
+            None => {
+                flow.tainted.remove(&name);
+            }
+        }
+    }
+
+    /// The collection `value` holds: a collection read, or a tainted name
+    /// used outside a subscript
+    fn collection(&self, value: Node, flow: &Flow) -> Option<Query> {
+        if let Some(query) = self.read(value, flow) {
+            return Some(query);
+        }
+        match value.kind() {
+            "identifier" => return flow.tainted.get(self.text(value)).cloned(),
+            "subscript" | "subscript_expression" => return None,
+            "call" | "call_expression" | "await" | "await_expression"
+                if bounded(&self.chain(value)) =>
+            {
+                return None
+            }
+            // `users.length`
+            "member_expression" | "attribute"
+                if value
+                    .child_by_field_name("property")
+                    .or_else(|| value.child_by_field_name("attribute"))
+                    .is_some_and(|p| matches!(self.text(p), "length" | "size")) =>
+            {
+                return None
+            }
+            _ => {}
+        }
+        let mut cursor = value.walk();
+        let children: Vec<Node> = value.named_children(&mut cursor).collect();
+        children.into_iter().find_map(|c| self.collection(c, flow))
+    }
+
+    /// The collection read `node` is, if it is one
+    fn read(&self, node: Node, flow: &Flow) -> Option<Query> {
+        if !matches!(
+            node.kind(),
+            "call" | "call_expression" | "await" | "await_expression"
+        ) {
+            return None;
+        }
+        let links = self.chain(node);
+        // `(await pool.query(sql)).rows`
+        let end = links
+            .iter()
+            .rposition(|l| l.arguments.is_some() || l.name == "[]")?;
+        let links = &links[..=end];
+        if bounded(links) {
+            return None;
+        }
+        let last = links.last()?;
+        let named = |name: &str| links.iter().position(|l| l.name == name);
+        let callee = links
+            .iter()
+            .map(|l| match l.arguments {
+                Some(_) => format!("{}()", l.name),
+                None => l.name.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(".");
+        let query = |orm, table: Option<&str>| {
+            Some(Query {
+                orm,
+                callee: callee.clone(),
+                table: table.map(str::to_string),
+            })
+        };
+        if let Some(sql) = self.sql_call(node) {
+            return Some(sql);
+        }
+        match (self.family, last.name) {
+            (Family::Python, name)
+                if DJANGO_READS.contains(&name) && named("objects").is_some() =>
+            {
+                let model = named("objects").and_then(|i| i.checked_sub(1));
+                query(Orm::Django, model.map(|i| links[i].name))
+            }
+            (Family::Python, "all") if links.len() > 1 => {
+                let table = links
+                    .iter()
+                    .filter(|l| matches!(l.name, "query" | "execute" | "select"))
+                    .find_map(|l| l.arguments.and_then(|a| self.model_argument(a)))
+                    .or_else(|| {
+                        named("query")
+                            .and_then(|i| i.checked_sub(1))
+                            .map(|i| links[i].name)
+                    });
+                query(Orm::SqlAlchemy, table)
+            }
+            (Family::Python, "fetchall") => flow.sql.clone(),
+            (Family::Js, "findMany") if !self.has_property(last.arguments, "take") => {
+                let model = links.len().checked_sub(2).map(|i| links[i].name);
+                query(Orm::Prisma, model)
+            }
+            (Family::Js, "findAll") if !self.has_property(last.arguments, "limit") => {
+                query(Orm::Sequelize, links.first().map(|l| l.name))
+            }
+            // `User.find({...}).sort(...)`
+            (Family::Js, _)
+                if links.len() > 1 && links[1].name == "find" && is_model(links[0].name) =>
+            {
+                let arguments = links[1].arguments?;
+                let mut cursor = arguments.walk();
+                let objects = arguments
+                    .named_children(&mut cursor)
+                    .all(|a| a.kind() == "object");
+                objects.then_some(())?;
+                query(Orm::Mongo, Some(links[0].name))
+            }
+            (Family::Js, "toArray") if named("find").is_some() => {
+                let table = named("collection")
+                    .and_then(|i| links[i].arguments)
+                    .and_then(|a| a.named_child(0))
+                    .map(|a| self.text(a).trim_matches(['"', '\'', '`']));
+                query(Orm::Mongo, table)
+            }
+            _ => None,
+        }
+    }
+
+    /// A SQL call whose first argument is an unbounded `SELECT` literal
+    fn sql_call(&self, node: Node) -> Option<Query> {
+        if !matches!(node.kind(), "call" | "call_expression") {
+            return None;
+        }
+        let function = node.child_by_field_name("function")?;
+        let name = match function.kind() {
+            "attribute" => function.child_by_field_name("attribute"),
+            "member_expression" => function.child_by_field_name("property"),
+            _ => Some(function),
+        }
+        .map(|n| self.text(n))?;
+        if !SQL_CALLS.contains(&name) {
+            return None;
+        }
+        let sql = node.child_by_field_name("arguments")?.named_child(0)?;
+        if !matches!(
+            sql.kind(),
+            "string" | "concatenated_string" | "template_string"
+        ) {
+            return None;
+        }
+        let table = unbounded_select(self.text(sql))?;
+        Some(Query {
+            orm: Orm::Sql,
+            callee: self.text(function).to_string(),
+            table: Some(table),
+        })
+    }
+
+    /// `User` in `query(User)`, `select(User)` or `execute(select(User))`
+    fn model_argument(&self, arguments: Node) -> Option<&'a str> {
+        let first = arguments.named_child(0)?;
+        match first.kind() {
+            "identifier" => Some(self.text(first)),
+            "call" => self.model_argument(first.child_by_field_name("arguments")?),
+            _ => None,
+        }
+    }
+
+    /// Whether the object passed in `arguments` sets `property`
+    fn has_property(&self, arguments: Option<Node>, property: &str) -> bool {
+        let Some(object) = arguments
+            .and_then(|a| a.named_child(0))
+            .filter(|a| a.kind() == "object")
+        else {
+            return false;
+        };
+        let mut cursor = object.walk();
+        let found = object.named_children(&mut cursor).any(|p| {
+            let key = match p.kind() {
+                "pair" => p.child_by_field_name("key"),
+                "shorthand_property_identifier" => Some(p),
+                _ => None,
+            };
+            key.is_some_and(|k| self.text(k).trim_matches(['"', '\'']) == property)
+        });
+        found
+    }
+
+    /// The links of the method chain ending in `node`, first to last:
+    /// `db.query(User).filter(x).all()` → `db`, `query(User)`, `filter(x)`,
+    /// `all()`
+    fn chain<'t>(&self, node: Node<'t>) -> Vec<Link<'t>>
+    where
+        'a: 't,
+    {
+        let mut links = Vec::new();
+        let mut arguments = None;
+        let mut node = node;
+        loop {
+            match node.kind() {
+                "call" | "call_expression" => {
+                    arguments = node.child_by_field_name("arguments");
+                    let Some(function) = node.child_by_field_name("function") else {
+                        break;
+                    };
+                    node = function;
+                }
+                "attribute" | "member_expression" => {
+                    let field = if node.kind() == "attribute" {
+                        "attribute"
+                    } else {
+                        "property"
+                    };
+                    let (Some(name), Some(object)) = (
+                        node.child_by_field_name(field),
+                        node.child_by_field_name("object"),
+                    ) else {
+                        break;
+                    };
+                    links.push(Link {
+                        name: self.text(name),
+                        arguments: arguments.take(),
+                    });
+                    node = object;
+                }
+                "subscript" | "subscript_expression" => {
+                    links.push(Link {
+                        name: "[]",
+                        arguments: None,
+                    });
+                    let field = if node.kind() == "subscript" {
+                        "value"
+                    } else {
+                        "object"
+                    };
+                    let Some(value) = node.child_by_field_name(field) else {
+                        break;
+                    };
+                    node = value;
+                }
+                "await" | "await_expression" | "parenthesized_expression" => {
+                    let Some(inner) = node.named_child(0) else {
+                        break;
+                    };
+                    node = inner;
+                }
+                "identifier" => {
+                    links.push(Link {
+                        name: self.text(node),
+                        arguments: arguments.take(),
+                    });
+                    break;
+                }
+                _ => break,
+            }
+        }
+        links.reverse();
+        links
+    }
+}
+
+/// Taint of one handler
+struct Flow {
+    /// Names holding a whole collection
+    tainted: HashMap<String, Query>,
+    /// The unbounded `SELECT` the handler runs, read by `fetchall()`
+    sql: Option<Query>,
+}
+
+fn is_js_function(kind: &str) -> bool {
+    matches!(kind, "arrow_function" | "function_expression" | "function")
+}
+
+/// Whether a chain reads one page, one row or a number
+fn bounded(links: &[Link]) -> bool {
+    links.iter().any(|l| BOUNDING.contains(&l.name))
+        || matches!(links, [only] if SCALARS.contains(&only.name))
+}
+
+/// `User` but not `users` or `db`: Mongoose models are capitalized
+fn is_model(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// The table of a `SELECT` that reads every row, from a string literal
+pub fn unbounded_select(sql: &str) -> Option<String> {
+    static SELECT: OnceLock<Regex> = OnceLock::new();
+    static BOUNDED: OnceLock<Regex> = OnceLock::new();
+    static AGGREGATE: OnceLock<Regex> = OnceLock::new();
+    let select = SELECT.get_or_init(|| {
+        Regex::new(r#"(?is)^[^A-Za-z]*select\b.*?\bfrom\s+[`"\[]?([\w.]+)"#).unwrap()
+    });
+    let bounded = BOUNDED.get_or_init(|| {
+        Regex::new(r"(?i)\blimit\b|\btop\s*\(?\s*\d|\bfetch\s+(first|next)\b|\boffset\b").unwrap()
+    });
+    let aggregate = AGGREGATE.get_or_init(|| {
+        Regex::new(r"(?is)^[^A-Za-z]*select\s+(count|sum|avg|min|max)\s*\(").unwrap()
+    });
+    // String prefixes: `f"..."`, `r"..."`
+    let sql = sql.trim_start_matches(['f', 'r', 'b', 'u', 'F', 'R', 'B', 'U']);
+    if bounded.is_match(sql) || aggregate.is_match(sql) {
+        return None;
+    }
+    select.captures(sql).map(|c| c[1].to_string())
+}
@@ -0,0 +1,102 @@
+//! Directory partitions of the analysis files, for progressive output
+//!
+//! A partition is a run of consecutive files, so running a per-file analyzer
+//! (see [`Analyzer::per_file`](super::Analyzer::per_file)) partition by
+//! partition and concatenating the results gives exactly the findings of
+//! one run over all files. Files are grouped by top-level directory; small
+//! neighbouring directories share a partition and a directory larger than
+//! the target size is split, so a big repository yields a handful of
+//! partitions (each one a CI log group and a burst of annotations) rather
+//! than hundreds.
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Partitions aimed for on a large repository
+pub const TARGET_PARTITIONS: usize = 16;
+
+/// Smallest partition worth its own flush
+pub const MIN_PARTITION_FILES: usize = 50;
+
+/// One partition of the files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partition {
+    /// Top-level directories the partition covers (`.` for files at the
+    /// root), in file order
+    pub dirs: Vec<String>,
+    /// Indices of its files in the list that was partitioned
+    pub files: Range<usize>,
+}
+
+impl Partition {
+    /// The directories as one label, e.g. `api/, web/`
+    pub fn label(&self) -> String {
+        self.dirs
+            .iter()
+            .map(|d| {
+                if d == "." {
+                    d.clone()
+                } else {
+                    format!("{}/", d)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Split `files` into partitions of about `files.len() / TARGET_PARTITIONS`
+/// files (at least [`MIN_PARTITION_FILES`])
+pub fn partition(files: &[PathBuf], repo_root: &Path) -> Vec<Partition> {
+    let size = files
+        .len()
+        .div_ceil(TARGET_PARTITIONS)
+        .max(MIN_PARTITION_FILES);
+    partition_by(files, repo_root, size)
+}
+
+/// Split `files` into partitions of at most `size` files, breaking at
+/// top-level directory boundaries where possible
+pub fn partition_by(files: &[PathBuf], repo_root: &Path, size: usize) -> Vec<Partition> {
+    let size = size.max(1);
+    let mut partitions: Vec<Partition> = Vec::new();
+    let mut start = 0;
+    while start < files.len() {
+        let dir = top_dir(&files[start], repo_root);
+        let end = files[start..]
+            .iter()
+            .position(|f| top_dir(f, repo_root) != dir)
+            .map_or(files.len(), |n| start + n);
+
+        // A directory joins the open partition when it still fits
+        match partitions.last_mut() {
+            Some(open) if open.files.len() + (end - start) <= size => {
+                open.dirs.push(dir);
+                open.files.end = end;
+            }
+            _ => {
+                let mut chunk = start;
+                while chunk < end {
+                    let chunk_end = (chunk + size).min(end);
+                    partitions.push(Partition {
+                        dirs: vec![dir.clone()],
+                        files: chunk..chunk_end,
+                    });
+                    chunk = chunk_end;
+                }
+            }
+        }
+        start = end;
+    }
+    partitions
+}
+
+/// First component of `file` relative to `repo_root`; `.` at the root
+fn top_dir(file: &Path, repo_root: &Path) -> String {
+    let rel = file.strip_prefix(repo_root).unwrap_or(file);
+    let mut components = rel.components();
+    match (components.next(), components.next()) {
+        (Some(first), Some(_)) => first.as_os_str().to_string_lossy().into_owned(),
+        _ => ".".to_string(),
+    }
+}
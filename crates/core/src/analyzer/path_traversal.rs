@@ -223,17 +223,19 @@ impl PathTraversalAnalyzer {
     }
 
     fn scan_file(path: &Path) -> Vec<Finding> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::scan_source(path, &content),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn scan_source(path: &Path, content: &str) -> Vec<Finding> {
         let ext = path
             .extension()
             .and_then(|e| e.to_str())
             .unwrap_or("")
             .to_lowercase();
 
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return Vec::new(),
-        };
-
         let all_patterns = patterns();
         let mut findings = Vec::new();
 
@@ -250,14 +252,17 @@ impl PathTraversalAnalyzer {
                         break;
                     }
                 }
-                findings.push(make_finding(
-                    pat.severity,
-                    format!("Possible path traversal: {}", pat.name),
-                    path.to_path_buf(),
-                    line_num + 1,
-                    Some(pat.suggestion.to_string()),
-                    Some(FixKind::Suggestion),
-                ));
+                findings.push(Finding {
+                    rule: Some("path_traversal".to_string()),
+                    ..make_finding(
+                        pat.severity,
+                        format!("Possible path traversal: {}", pat.name),
+                        path.to_path_buf(),
+                        line_num + 1,
+                        Some(pat.suggestion.to_string()),
+                        Some(FixKind::Suggestion),
+                    )
+                });
                 break; // One finding per line
             }
         }
@@ -285,6 +290,22 @@ impl Analyzer for PathTraversalAnalyzer {
         config.modules.security
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("security")
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        &["path_traversal"]
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Self::should_scan(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
     fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
         let mut findings = Vec::new();
         for file in files {
@@ -295,4 +316,11 @@ impl Analyzer for PathTraversalAnalyzer {
         }
         findings
     }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        if !Self::should_scan(path) {
+            return Vec::new();
+        }
+        Self::scan_source(path, content)
+    }
 }
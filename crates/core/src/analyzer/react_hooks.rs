@@ -5,23 +5,34 @@
 //! (first matching pattern wins) to reduce noise.
 
 use crate::analyzer::{make_finding, Analyzer};
-use crate::config::RevetConfig;
-use crate::finding::{Finding, FixKind, Severity};
+use crate::config::{RevetConfig, RuleSelection};
+use crate::finding::{Finding, FixKind, InsertContext, Severity, SuggestedCode};
+use crate::suggestion;
 use regex::Regex;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
+/// Builds the corrected code from the file and the byte offset of the
+/// matched line
+type CodeFn = fn(&Path, &str, usize) -> Option<SuggestedCode>;
+
 /// A compiled React hooks detection pattern
 struct HookPattern {
+    /// Sub-rule id (`react:<rule>`)
+    rule: &'static str,
     name: &'static str,
     regex: Regex,
     severity: Severity,
     /// If set, skip the match when the line contains this substring
     reject_if_contains: Option<&'static str>,
     suggestion: &'static str,
+    code: Option<CodeFn>,
     fix_kind: FixKind,
 }
 
+/// Longest call [`effect_with_deps`] rewrites, in lines
+const MAX_EFFECT_LINES: usize = 12;
+
 /// Returns all hook patterns in priority order (Error patterns first)
 fn patterns() -> &'static [HookPattern] {
     static PATTERNS: OnceLock<Vec<HookPattern>> = OnceLock::new();
@@ -29,31 +40,38 @@ fn patterns() -> &'static [HookPattern] {
         vec![
             // --- Error: Rules of Hooks violations ---
             HookPattern {
+                rule: "hook_in_condition",
                 name: "Hook inside condition",
                 regex: Regex::new(r"if\s*\(.*\).*\buse[A-Z]\w+\s*\(").unwrap(),
                 severity: Severity::Error,
                 reject_if_contains: None,
                 suggestion: "Move hook call to top level of the component",
+                code: None,
                 fix_kind: FixKind::Suggestion,
             },
             HookPattern {
+                rule: "hook_in_loop",
                 name: "Hook inside loop",
                 regex: Regex::new(r"(?:for\s*\(|while\s*\().*\buse[A-Z]\w+\s*\(").unwrap(),
                 severity: Severity::Error,
                 reject_if_contains: None,
                 suggestion: "Move hook call to top level of the component",
+                code: None,
                 fix_kind: FixKind::Suggestion,
             },
             // --- Warning: Common anti-patterns ---
             HookPattern {
+                rule: "effect_without_deps",
                 name: "useEffect without dependency array",
                 regex: Regex::new(r"useEffect\s*\(").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: Some(", ["),
-                suggestion: "Add dependency array: useEffect(() => { ... }, [deps])",
+                suggestion: "Add a dependency array listing the props and state the effect reads",
+                code: Some(effect_with_deps),
                 fix_kind: FixKind::Suggestion,
             },
             HookPattern {
+                rule: "dom_manipulation",
                 name: "Direct DOM manipulation",
                 regex: Regex::new(
                     r"document\.(?:getElementById|querySelector|querySelectorAll|getElementsBy)\s*\(",
@@ -62,56 +80,163 @@ fn patterns() -> &'static [HookPattern] {
                 severity: Severity::Warning,
                 reject_if_contains: None,
                 suggestion: "Use useRef() hook instead of direct DOM manipulation",
+                code: None,
                 fix_kind: FixKind::Suggestion,
             },
             HookPattern {
+                rule: "missing_key",
                 name: "Missing key prop in map",
                 regex: Regex::new(r"\.map\s*\(.*=>\s*<[A-Z]").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: Some("key="),
-                suggestion: "Add a unique key prop: <Component key={item.id} />",
+                suggestion: "Add a key prop with a stable, unique ID to the element the callback returns",
+                code: Some(keyed_element),
                 fix_kind: FixKind::Suggestion,
             },
             HookPattern {
+                rule: "dangerous_html",
                 name: "dangerouslySetInnerHTML usage",
                 regex: Regex::new(r"dangerouslySetInnerHTML").unwrap(),
                 severity: Severity::Warning,
                 reject_if_contains: None,
                 suggestion:
                     "Avoid dangerouslySetInnerHTML; use a sanitization library like DOMPurify if needed",
+                code: None,
                 fix_kind: FixKind::Suggestion,
             },
             // --- Info: Performance hints ---
             HookPattern {
+                rule: "inline_handler",
                 name: "Inline function in JSX event handler",
                 regex: Regex::new(r"on[A-Z]\w+=\{.*=>").unwrap(),
                 severity: Severity::Info,
                 reject_if_contains: None,
                 suggestion:
                     "Extract handler to useCallback() to prevent unnecessary re-renders",
+                code: None,
                 fix_kind: FixKind::Suggestion,
             },
             HookPattern {
+                rule: "effect_empty_deps",
                 name: "useEffect with empty dependency array",
                 regex: Regex::new(r"useEffect\s*\(.*,\s*\[\s*\]\s*\)").unwrap(),
                 severity: Severity::Info,
                 reject_if_contains: None,
                 suggestion: "Empty dependency array means this runs once on mount; ensure it does not reference props or state that may change",
+                code: None,
                 fix_kind: FixKind::Suggestion,
             },
         ]
     })
 }
 
+/// The `useEffect(...)` call starting on the line at `offset`, with an
+/// empty dependency array added
+fn effect_with_deps(path: &Path, content: &str, offset: usize) -> Option<SuggestedCode> {
+    let start = offset + content[offset..].find("useEffect")?;
+    let open = start + content[start..].find('(')?;
+    let close = matching_paren(content, open)?;
+    let original = &content[start..=close];
+    if original.lines().count() > MAX_EFFECT_LINES {
+        return None;
+    }
+    let args = content[open + 1..close].trim_end();
+    let args = args.strip_suffix(',').unwrap_or(args);
+    suggestion::code(
+        path,
+        format!("useEffect({}, [])", args),
+        InsertContext::Replace {
+            original: original.to_string(),
+        },
+    )
+}
+
+/// The line at `offset` with `key={<param>.id}` added to the element the
+/// `.map()` callback returns; `None` for destructured parameters
+fn keyed_element(path: &Path, content: &str, offset: usize) -> Option<SuggestedCode> {
+    static CALLBACK: OnceLock<Regex> = OnceLock::new();
+    let callback = CALLBACK.get_or_init(|| {
+        Regex::new(r"\.map\s*\(\s*\(?\s*([A-Za-z_$][\w$]*)[^=]*?=>\s*\(?\s*<([A-Z][\w.]*)").unwrap()
+    });
+    let line = content[offset..].lines().next()?;
+    let caps = callback.captures(line)?;
+    let tag_end = caps.get(2)?.end();
+    let snippet = format!(
+        "{} key={{{}.id}}{}",
+        &line[..tag_end],
+        &caps[1],
+        &line[tag_end..]
+    );
+    suggestion::code(path, snippet.trim(), InsertContext::Line)
+}
+
+/// Byte offset of the `)` closing the `(` at `open`, skipping strings and
+/// comments
+fn matching_paren(content: &str, open: usize) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return (bytes[i] == b')').then_some(i);
+                }
+            }
+            quote @ (b'"' | b'\'' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i += content[i..].find('\n')?;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += content[i..].find("*/")? + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
 /// React file extensions to scan
 const REACT_EXTENSIONS: &[&str] = &["tsx", "jsx", "ts", "js"];
 
+/// Sub-rule ids, in pattern order
+const RULES: &[&str] = &[
+    "hook_in_condition",
+    "hook_in_loop",
+    "effect_without_deps",
+    "dom_manipulation",
+    "missing_key",
+    "dangerous_html",
+    "inline_handler",
+    "effect_empty_deps",
+];
+
 /// Analyzer that detects React hooks misuse and common anti-patterns
-pub struct ReactHooksAnalyzer;
+pub struct ReactHooksAnalyzer {
+    rules: RuleSelection,
+}
 
 impl ReactHooksAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self::with_rules(&RuleSelection::default())
+    }
+
+    /// Create an analyzer that only tries the sub-rules `rules` selects
+    pub fn with_rules(rules: &RuleSelection) -> Self {
+        Self {
+            rules: rules.clone(),
+        }
     }
 
     /// Check if a file should be scanned based on its extension
@@ -132,12 +257,14 @@ impl ReactHooksAnalyzer {
     }
 
     /// Scan a single file for React hooks issues
-    fn scan_file(path: &Path) -> Vec<Finding> {
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return Vec::new(),
-        };
+    fn scan_file(&self, path: &Path) -> Vec<Finding> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => self.scan_source(path, &content),
+            Err(_) => Vec::new(),
+        }
+    }
 
+    fn scan_source(&self, path: &Path, content: &str) -> Vec<Finding> {
         let all_patterns = patterns();
         let mut findings = Vec::new();
 
@@ -148,6 +275,9 @@ impl ReactHooksAnalyzer {
 
             // First matching pattern wins for this line
             for pat in all_patterns {
+                if !self.rules.allows(pat.rule) {
+                    continue;
+                }
                 if pat.regex.is_match(line) {
                     // Check reject filter
                     if let Some(reject) = pat.reject_if_contains {
@@ -156,14 +286,20 @@ impl ReactHooksAnalyzer {
                         }
                     }
 
-                    findings.push(make_finding(
-                        pat.severity,
-                        pat.name.to_string(),
-                        path.to_path_buf(),
-                        line_num + 1,
-                        Some(pat.suggestion.to_string()),
-                        Some(pat.fix_kind.clone()),
-                    ));
+                    // `lines()` yields subslices of `content`
+                    let offset = line.as_ptr() as usize - content.as_ptr() as usize;
+                    findings.push(Finding {
+                        rule: Some(pat.rule.to_string()),
+                        suggestion_code: pat.code.and_then(|code| code(path, content, offset)),
+                        ..make_finding(
+                            pat.severity,
+                            pat.name.to_string(),
+                            path.to_path_buf(),
+                            line_num + 1,
+                            Some(pat.suggestion.to_string()),
+                            Some(pat.fix_kind.clone()),
+                        )
+                    });
                     break;
                 }
             }
@@ -192,6 +328,22 @@ impl Analyzer for ReactHooksAnalyzer {
         config.modules.react
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("react")
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Self::should_scan(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
     fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
         let mut findings = Vec::new();
 
@@ -199,9 +351,16 @@ impl Analyzer for ReactHooksAnalyzer {
             if !Self::should_scan(file) {
                 continue;
             }
-            findings.extend(Self::scan_file(file));
+            findings.extend(self.scan_file(file));
         }
 
         findings
     }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        if !Self::should_scan(path) {
+            return Vec::new();
+        }
+        self.scan_source(path, content)
+    }
 }
@@ -0,0 +1,263 @@
+//! Refactor verification — call sites a diff forgot to update
+//!
+//! In diff mode, the top-level functions of every modified Python,
+//! JavaScript or TypeScript file are compared with their base version:
+//!
+//! - `arity_mismatch`: a function whose accepted argument count changed. Each
+//!   caller in the head graph whose call line the diff did not touch is
+//!   checked: a call that fit the old signature and no longer fits the new
+//!   one is reported at the call, with both signatures.
+//! - `stale_reference`: a function removed while one with the same
+//!   parameters was added to the same file — a rename. Calls to the old name
+//!   left in that file, and imports of it from other files, are reported
+//!   when the diff did not touch their line.
+//!
+//! Only dynamic languages are checked; compilers catch the rest. Functions
+//! with variadic or keyword-only parameters, overloads, spread arguments,
+//! methods and calls the graph does not link to their callee are skipped,
+//! so every finding is a call that will fail at runtime.
+//!
+//! Needs the head graph of the whole repository, so that callers in files
+//! the diff did not touch are linked, and the base side of the diff (see
+//! [`DeltaBase`]).
+//!
+//! Finding prefix: `REF-`
+
+pub mod syntax;
+
+use crate::analyzer::catalog_finding;
+use crate::analyzer::complexity::delta::DeltaBase;
+use crate::finding::{Finding, Severity};
+use crate::graph::{CodeGraph, EdgeKind, EdgeMetadata, NodeData, NodeKind};
+use crate::msg;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use syntax::Signature;
+
+/// Module name of refactor verification
+pub const MODULE: &str = "refactor";
+
+/// Sub-rules of refactor verification
+pub const RULES: &[&str] = &["arity_mismatch", "stale_reference"];
+
+/// Head sources, read once each
+#[derive(Default)]
+struct Sources {
+    files: HashMap<PathBuf, Option<String>>,
+}
+
+impl Sources {
+    fn get(&mut self, path: &Path) -> Option<&str> {
+        self.files
+            .entry(path.to_path_buf())
+            .or_insert_with(|| std::fs::read_to_string(path).ok())
+            .as_deref()
+    }
+}
+
+/// Call sites in `head` the diff against `base` left behind
+pub fn analyze(head: &CodeGraph, base: &DeltaBase) -> Vec<Finding> {
+    let mut sources = Sources::default();
+    let mut findings = Vec::new();
+
+    let mut modified: Vec<(&Path, &str)> = base
+        .sources()
+        .filter(|(path, _)| syntax::family(path).is_some())
+        .collect();
+    modified.sort();
+    for (file, base_source) in modified {
+        let Some(head_source) = sources.get(file).map(String::from) else {
+            continue;
+        };
+        let before = syntax::definitions(file, base_source);
+        let after = syntax::definitions(file, &head_source);
+        let check = Check {
+            head,
+            base,
+            file,
+            rel: file.strip_prefix(base.repo_root()).unwrap_or(file),
+        };
+
+        for new in &after {
+            let Some(old) = before.iter().find(|b| b.name == new.name) else {
+                continue;
+            };
+            if let (Some(was), Some(now)) = (old.arity, new.arity) {
+                if was != now {
+                    findings.extend(check.arity_mismatches(old, new, &mut sources));
+                }
+            }
+        }
+
+        let removed: Vec<&Signature> = before
+            .iter()
+            .filter(|b| !after.iter().any(|a| a.name == b.name))
+            .collect();
+        let added: Vec<&Signature> = after
+            .iter()
+            .filter(|a| !before.iter().any(|b| b.name == a.name))
+            .collect();
+        for old in &removed {
+            let same = |a: &&&Signature, b: &Signature| a.params == b.params;
+            let mut candidates = added.iter().filter(|a| same(a, old));
+            let (Some(new), None) = (candidates.next(), candidates.next()) else {
+                continue;
+            };
+            // The pair must be unambiguous from both sides
+            if removed.iter().filter(|r| same(&new, r)).count() != 1 {
+                continue;
+            }
+            findings.extend(check.stale_references(old, new, &head_source));
+        }
+    }
+    findings
+}
+
+/// One modified file
+struct Check<'a> {
+    head: &'a CodeGraph,
+    base: &'a DeltaBase,
+    file: &'a Path,
+    rel: &'a Path,
+}
+
+impl Check<'_> {
+    /// Unchanged calls to `new` that fit the `old` signature only
+    fn arity_mismatches(
+        &self,
+        old: &Signature,
+        new: &Signature,
+        sources: &mut Sources,
+    ) -> Vec<Finding> {
+        let (Some(was), Some(now)) = (old.arity, new.arity) else {
+            return Vec::new();
+        };
+        let file = self.rel.to_string_lossy();
+        let mut seen = HashSet::new();
+        let mut findings = Vec::new();
+        let mut nodes: Vec<_> = self
+            .head
+            .nodes()
+            .filter(|(_, n)| {
+                matches!(n.kind(), NodeKind::Function)
+                    && n.file_path() == self.file
+                    && n.name() == new.name
+            })
+            .collect();
+        // Methods of the same name live in the file too
+        if nodes.len() > 1 {
+            nodes.retain(|(_, n)| n.line() == new.line);
+        }
+        for (id, _) in nodes {
+            for (caller, edge) in self.head.edges_to(id) {
+                let (EdgeKind::Calls, Some(EdgeMetadata::Call { line, .. })) =
+                    (edge.kind(), edge.metadata())
+                else {
+                    continue;
+                };
+                let Some(caller) = self.head.node(caller) else {
+                    continue;
+                };
+                let path = caller.file_path();
+                if self.base.is_changed(path, *line) || !seen.insert((path.clone(), *line)) {
+                    continue;
+                }
+                let Some(source) = sources.get(path) else {
+                    continue;
+                };
+                let stale = syntax::calls(path, source)
+                    .into_iter()
+                    .filter(|c| c.line == *line && c.name == new.name)
+                    .filter_map(|c| c.args)
+                    .find(|&n| was.accepts(n) && !now.accepts(n));
+                let Some(count) = stale else {
+                    continue;
+                };
+                findings.push(Finding {
+                    rule: Some("arity_mismatch".to_string()),
+                    ..catalog_finding(
+                        Severity::Error,
+                        msg!(
+                            "refactor-arity-mismatch",
+                            name = &new.name,
+                            count = count,
+                            new = new.display(),
+                            old = old.display()
+                        ),
+                        path.clone(),
+                        *line,
+                        Some(msg!("refactor-arity-mismatch.suggestion", file = &file)),
+                        None,
+                    )
+                });
+            }
+        }
+        findings.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        findings
+    }
+
+    /// Unchanged references to `old`, renamed to `new`: bare calls in the
+    /// file itself and imports from other files
+    fn stale_references(&self, old: &Signature, new: &Signature, source: &str) -> Vec<Finding> {
+        let imports = |path: &Path| -> Vec<(PathBuf, usize)> {
+            self.head
+                .nodes()
+                .filter_map(|(_, n)| match n.data() {
+                    NodeData::Import {
+                        imported_names,
+                        resolved_path,
+                        ..
+                    } if imported_names.contains(&old.name)
+                        && (n.file_path() == path || resolved_path.as_deref() == Some(path)) =>
+                    {
+                        Some((n.file_path().clone(), n.line()))
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let mut sites: Vec<(PathBuf, usize)> = Vec::new();
+        let (local, remote): (Vec<_>, Vec<_>) = imports(self.file)
+            .into_iter()
+            .partition(|(path, _)| path == self.file);
+        // Imported under the old name from elsewhere: not this function
+        if local.is_empty() {
+            sites.extend(
+                syntax::calls(self.file, source)
+                    .into_iter()
+                    .filter(|c| c.bare && c.name == old.name)
+                    .map(|c| (self.file.to_path_buf(), c.line)),
+            );
+        }
+        sites.extend(remote);
+        sites.sort();
+        sites.dedup();
+
+        let file = self.rel.to_string_lossy();
+        sites
+            .into_iter()
+            .filter(|(path, line)| !self.base.is_changed(path, *line))
+            .map(|(path, line)| Finding {
+                rule: Some("stale_reference".to_string()),
+                ..catalog_finding(
+                    Severity::Error,
+                    msg!(
+                        "refactor-stale-reference",
+                        old = &old.name,
+                        new = &new.name,
+                        file = &file
+                    ),
+                    path,
+                    line,
+                    Some(msg!(
+                        "refactor-stale-reference.suggestion",
+                        old = &old.name,
+                        new = &new.name
+                    )),
+                    None,
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,285 @@
+//! Top-level function signatures and call arguments, read with tree-sitter
+//!
+//! The graph keeps parameter names but drops `*args`, rest parameters and
+//! keyword-only markers, so the arity of a signature is read from the source
+//! here. Anything that makes the accepted argument count open-ended —
+//! variadics, keyword-only parameters, TypeScript overloads — leaves the
+//! signature without an arity, and such functions are never checked.
+
+use crate::parser::grammar::{language_for, Family, Grammar};
+use std::collections::HashSet;
+use std::path::Path;
+use tree_sitter::{Node, Tree};
+
+/// Number of arguments a function accepts: `min..=max`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arity {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Arity {
+    pub fn accepts(&self, count: usize) -> bool {
+        (self.min..=self.max).contains(&count)
+    }
+}
+
+/// A top-level function definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub name: String,
+    /// Line of the definition (not of its decorators)
+    pub line: usize,
+    /// Parameter list as written, whitespace collapsed: `(amount, currency)`
+    pub params: String,
+    /// `None` when the accepted argument count is open-ended
+    pub arity: Option<Arity>,
+}
+
+impl Signature {
+    /// `charge(amount, currency)`
+    pub fn display(&self) -> String {
+        format!("{}{}", self.name, self.params)
+    }
+}
+
+/// A call expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    pub line: usize,
+    /// Last segment of the callee: `charge` in `billing.charge(...)`
+    pub name: String,
+    /// Whether the callee is a bare name rather than a member access
+    pub bare: bool,
+    /// `None` when an argument is spread (`*args`, `...rest`)
+    pub args: Option<usize>,
+}
+
+/// Family of `path`, for the languages whose call sites only fail at runtime
+pub fn family(path: &Path) -> Option<Family> {
+    grammar(path).map(|g| g.family)
+}
+
+fn grammar(path: &Path) -> Option<Grammar> {
+    language_for(path).filter(|g| matches!(g.family, Family::Python | Family::Js))
+}
+
+fn parse(path: &Path, source: &str) -> Option<(Family, Tree)> {
+    let grammar = grammar(path)?;
+    Some((grammar.family, grammar.parse(source)?))
+}
+
+/// Top-level function definitions of one source, in source order. A name
+/// defined more than once, or overloaded, has no arity.
+pub fn definitions(path: &Path, source: &str) -> Vec<Signature> {
+    let Some((family, tree)) = parse(path, source) else {
+        return Vec::new();
+    };
+    let reader = Reader {
+        family,
+        source: source.as_bytes(),
+    };
+    let mut overloaded = HashSet::new();
+    let mut signatures: Vec<Signature> = Vec::new();
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    for child in root.named_children(&mut cursor) {
+        reader.top_level(child, &mut signatures, &mut overloaded);
+    }
+    let mut seen = HashSet::new();
+    let repeated: HashSet<String> = signatures
+        .iter()
+        .filter(|s| !seen.insert(s.name.clone()))
+        .map(|s| s.name.clone())
+        .collect();
+    for signature in &mut signatures {
+        if repeated.contains(&signature.name) || overloaded.contains(&signature.name) {
+            signature.arity = None;
+        }
+    }
+    signatures
+}
+
+/// Every call in one source, in source order
+pub fn calls(path: &Path, source: &str) -> Vec<Call> {
+    let Some((family, tree)) = parse(path, source) else {
+        return Vec::new();
+    };
+    let reader = Reader {
+        family,
+        source: source.as_bytes(),
+    };
+    let mut calls = Vec::new();
+    reader.collect_calls(tree.root_node(), &mut calls);
+    calls
+}
+
+struct Reader<'a> {
+    family: Family,
+    source: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.source).unwrap_or("")
+    }
+
+    fn top_level(&self, node: Node, out: &mut Vec<Signature>, overloaded: &mut HashSet<String>) {
+        match (self.family, node.kind()) {
+            (Family::Python, "function_definition") | (Family::Js, "function_declaration") => {
+                out.extend(self.signature(node, node));
+            }
+            (Family::Python, "decorated_definition") => {
+                if let Some(def) = node.child_by_field_name("definition") {
+                    self.top_level(def, out, overloaded);
+                }
+            }
+            (Family::Js, "export_statement") => {
+                if let Some(decl) = node.child_by_field_name("declaration") {
+                    self.top_level(decl, out, overloaded);
+                }
+            }
+            (Family::Js, "function_signature") => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    overloaded.insert(self.text(name).to_string());
+                }
+            }
+            (Family::Js, "lexical_declaration") => {
+                let mut cursor = node.walk();
+                for declarator in node.named_children(&mut cursor) {
+                    let (Some(name), Some(value)) = (
+                        declarator.child_by_field_name("name"),
+                        declarator.child_by_field_name("value"),
+                    ) else {
+                        continue;
+                    };
+                    if name.kind() == "identifier"
+                        && matches!(value.kind(), "arrow_function" | "function_expression")
+                    {
+                        out.extend(self.signature(name, value));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Signature of `function`, named by the `name` field of `named`
+    fn signature(&self, named: Node, function: Node) -> Option<Signature> {
+        let name = if named.kind() == "identifier" {
+            named
+        } else {
+            named.child_by_field_name("name")?
+        };
+        let line = named.start_position().row + 1;
+        if let Some(param) = function.child_by_field_name("parameter") {
+            // `x => ...`
+            return Some(Signature {
+                name: self.text(name).to_string(),
+                line,
+                params: format!("({})", self.text(param)),
+                arity: Some(Arity { min: 1, max: 1 }),
+            });
+        }
+        let params = function.child_by_field_name("parameters")?;
+        Some(Signature {
+            name: self.text(name).to_string(),
+            line,
+            params: collapse(self.text(params)),
+            arity: self.arity(params),
+        })
+    }
+
+    fn arity(&self, params: Node) -> Option<Arity> {
+        let (mut min, mut max) = (0, 0);
+        let mut cursor = params.walk();
+        for param in params.named_children(&mut cursor) {
+            let kind = match param.kind() {
+                // `*args: int`
+                "typed_parameter" => param.named_child(0).map(|c| c.kind()).unwrap_or(""),
+                // TypeScript: `...rest: T[]`, `x = 1`, `this: Window`
+                "required_parameter" => {
+                    let pattern = param.child_by_field_name("pattern")?;
+                    match pattern.kind() {
+                        "rest_pattern" => "rest_pattern",
+                        "this" => continue,
+                        _ if param.child_by_field_name("value").is_some() => "optional_parameter",
+                        _ => "identifier",
+                    }
+                }
+                kind => kind,
+            };
+            match kind {
+                "comment" | "positional_separator" => {}
+                "default_parameter"
+                | "typed_default_parameter"
+                | "optional_parameter"
+                | "assignment_pattern" => max += 1,
+                "list_splat_pattern"
+                | "dictionary_splat_pattern"
+                | "keyword_separator"
+                | "rest_pattern" => return None,
+                _ => {
+                    min += 1;
+                    max += 1;
+                }
+            }
+        }
+        Some(Arity { min, max })
+    }
+
+    fn collect_calls(&self, node: Node, out: &mut Vec<Call>) {
+        if matches!(node.kind(), "call" | "call_expression") {
+            if let Some(call) = self.call(node) {
+                out.push(call);
+            }
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.collect_calls(child, out);
+        }
+    }
+
+    fn call(&self, node: Node) -> Option<Call> {
+        let callee = node.child_by_field_name("function")?;
+        let (name, bare) = match callee.kind() {
+            "identifier" => (callee, true),
+            "attribute" => (callee.child_by_field_name("attribute")?, false),
+            "member_expression" => (callee.child_by_field_name("property")?, false),
+            _ => return None,
+        };
+        let arguments = node.child_by_field_name("arguments")?;
+        let args = match arguments.kind() {
+            "argument_list" | "arguments" => {
+                let mut cursor = arguments.walk();
+                let mut count = Some(0);
+                for arg in arguments.named_children(&mut cursor) {
+                    match arg.kind() {
+                        "comment" => {}
+                        "list_splat" | "dictionary_splat" | "spread_element" => count = None,
+                        _ => count = count.map(|c| c + 1),
+                    }
+                }
+                count
+            }
+            // `f(x for x in xs)`
+            "generator_expression" => Some(1),
+            // Tagged templates
+            _ => return None,
+        };
+        Some(Call {
+            line: node.start_position().row + 1,
+            name: self.text(name).to_string(),
+            bare,
+            args,
+        })
+    }
+}
+
+fn collapse(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("( ", "(")
+        .replace(" )", ")")
+}
@@ -0,0 +1,392 @@
+//! Finding-ID namespaces — who may mint which finding IDs
+//!
+//! A finding ID is `PREFIX-NNN`. Baselines, suppressions and `[ignore]`
+//! all key on the prefix, so two sources sharing one would silently merge.
+//! The prefixes of built-in analyzers are reserved ([`BUILTIN`]); findings
+//! from user config use `CUSTOM` or a prefix set with `[[rules]] prefix`,
+//! and plugins `PLUGIN-<name>`. Sub-rule ids starting with `custom-` are
+//! likewise left to configured detectors (`[[analyzers.secrets.patterns]]`).
+//!
+//! [`Registry`] records the claimant of every prefix and sub-rule; a second
+//! claim by someone else is an error naming both.
+
+use super::{rules, secret_exposure};
+use crate::config::RevetConfig;
+use crate::finding::Finding;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A finding prefix reserved for a built-in producer
+pub struct Builtin {
+    pub prefix: &'static str,
+    pub name: &'static str,
+}
+
+const fn builtin(prefix: &'static str, name: &'static str) -> Builtin {
+    Builtin { prefix, name }
+}
+
+/// Every built-in finding prefix: the analyzers, then the pipeline's own
+pub const BUILTIN: &[Builtin] = &[
+    builtin("SEC", "Secret exposure"),
+    builtin("SQL", "SQL injection"),
+    builtin("CMD", "Command injection"),
+    builtin("DESER", "Insecure deserialization"),
+    builtin("SSRF", "Server-side request forgery"),
+    builtin("PATH", "Path traversal"),
+    builtin("LOG", "Sensitive data in logs"),
+    builtin("ENDPT", "Hardcoded endpoints"),
+    builtin("MAGIC", "Magic numbers"),
+    builtin("RES", "Resource leaks"),
+    builtin("ML", "ML pipeline"),
+    builtin("INFRA", "Infrastructure"),
+    builtin("K8S", "Kubernetes"),
+    builtin("HOOKS", "React hooks"),
+    builtin("ASYNC", "Async patterns"),
+    builtin("DEP", "Dependency hygiene"),
+    builtin("ERR", "Error handling"),
+    builtin("TOOL", "Toolchain consistency"),
+    builtin("DUP", "Duplication"),
+    builtin("SKEW", "Monorepo skew"),
+    builtin("I18N", "Translations"),
+    builtin("FLAG", "Feature flags"),
+    builtin("DBG", "Debug artifacts"),
+    builtin("TEST", "Test quality"),
+    builtin("COPY", "Stale copy"),
+    builtin("NUM", "Numeric precision"),
+    builtin("PAGE", "Pagination"),
+    builtin("A11Y", "Accessibility"),
+    builtin("WEBSEC", "Web security configuration"),
+    builtin("CONTRACT", "API contract"),
+    builtin("REPO", "Repository"),
+    builtin("GEN", "Generated code"),
+    builtin("DEAD", "Unused exports"),
+    builtin("CYCLE", "Circular imports"),
+    builtin("BOUNDARY", "Import boundaries"),
+    builtin("CMPLX", "Complexity"),
+    builtin("IMP", "Dead imports"),
+    builtin("COV", "Test coverage gaps"),
+    builtin("COUPLE", "Coupling"),
+    builtin("DOC", "Documentation coverage"),
+    builtin("RACE", "Concurrency hazards"),
+    builtin("UPGRADE", "Upgrade advisor"),
+    builtin("SUPP", "Suppression audit"),
+    builtin("CONF", "Configuration drift"),
+    builtin("HOT", "Hotspots"),
+    builtin("OWN", "Ownership"),
+    builtin("REF", "Refactor verification"),
+    builtin("PARSE", "Parse errors"),
+    builtin("IMPACT", "Change impact"),
+    builtin("BREAKING", "Breaking changes"),
+    builtin("HIST", "Secrets in git history"),
+    builtin("PERF", "Slow files"),
+    builtin("EXT", "Imported findings"),
+];
+
+/// Prefix of `[[rules]]` that set none
+pub const CUSTOM: &str = "CUSTOM";
+
+/// Prefix every plugin prefix starts with (`PLUGIN-<name>`)
+pub const PLUGIN: &str = "PLUGIN";
+
+/// Where a finding comes from
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Source {
+    /// A built-in analyzer or pipeline stage
+    Builtin,
+    /// `.revet.toml`: `[[rules]]` or a custom secret pattern
+    Custom,
+    /// The named plugin
+    Plugin(String),
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Builtin => write!(f, "builtin"),
+            Source::Custom => write!(f, "custom"),
+            Source::Plugin(name) => write!(f, "plugin:{}", name),
+        }
+    }
+}
+
+impl From<Source> for String {
+    fn from(source: Source) -> Self {
+        source.to_string()
+    }
+}
+
+impl TryFrom<String> for Source {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "builtin" => Ok(Source::Builtin),
+            "custom" => Ok(Source::Custom),
+            _ => match value.strip_prefix("plugin:") {
+                Some(name) if !name.is_empty() => Ok(Source::Plugin(name.to_string())),
+                _ => Err(format!(
+                    "unknown finding source {:?} (expected builtin, custom or plugin:<name>)",
+                    value
+                )),
+            },
+        }
+    }
+}
+
+/// Prefix of a finding ID: `SEC-001` → `SEC`, `PLUGIN-acme-002` →
+/// `PLUGIN-acme`. An ID without a number is its own prefix.
+pub fn prefix_of(id: &str) -> &str {
+    match id.rsplit_once('-') {
+        Some((prefix, number))
+            if !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            prefix
+        }
+        _ if id.starts_with("PLUGIN-") => id,
+        _ => id.split('-').next().unwrap_or(id),
+    }
+}
+
+/// Source of `finding`, read off its ID and sub-rule. Built-in prefixes
+/// are reserved, so the ID alone tells built-in findings from the rest.
+pub fn source_of(finding: &Finding) -> Source {
+    let prefix = prefix_of(&finding.id);
+    if let Some(name) = prefix
+        .strip_prefix(PLUGIN)
+        .and_then(|p| p.strip_prefix('-'))
+    {
+        return Source::Plugin(name.to_string());
+    }
+    if !is_builtin(prefix) {
+        return Source::Custom;
+    }
+    let custom_rule = finding
+        .rule
+        .as_deref()
+        .and_then(|r| r.split_once(':'))
+        .is_some_and(|(_, rule)| rule.starts_with("custom-"));
+    if custom_rule {
+        Source::Custom
+    } else {
+        Source::Builtin
+    }
+}
+
+/// Whether `prefix` is reserved for a built-in producer
+pub fn is_builtin(prefix: &str) -> bool {
+    BUILTIN.iter().any(|b| b.prefix == prefix)
+}
+
+/// Who claims a prefix or sub-rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Claim {
+    /// Human-readable claimant, e.g. `Secret exposure` or `[[rules]] "no-todo"`
+    pub claimant: String,
+    pub source: Source,
+}
+
+impl Claim {
+    /// Whether `other` may claim what `self` already has
+    fn compatible(&self, other: &Claim) -> bool {
+        self.source == other.source
+            && (self.claimant == other.claimant || self.source == Source::Custom)
+    }
+}
+
+impl std::fmt::Display for Claim {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.claimant, self.source)
+    }
+}
+
+/// Claimed finding prefixes and sub-rule ids
+#[derive(Debug, Clone)]
+pub struct Registry {
+    prefixes: BTreeMap<String, Claim>,
+    /// `module:rule` → claim
+    rules: BTreeMap<String, Claim>,
+}
+
+impl Registry {
+    /// The built-in prefixes and sub-rules, plus `CUSTOM`
+    pub fn new() -> Self {
+        let mut registry = Self {
+            prefixes: BTreeMap::new(),
+            rules: BTreeMap::new(),
+        };
+        for builtin in BUILTIN {
+            registry.prefixes.insert(
+                builtin.prefix.to_string(),
+                Claim {
+                    claimant: builtin.name.to_string(),
+                    source: Source::Builtin,
+                },
+            );
+        }
+        registry.prefixes.insert(
+            CUSTOM.to_string(),
+            Claim {
+                claimant: "[[rules]]".to_string(),
+                source: Source::Custom,
+            },
+        );
+        for (module, sub_rules) in rules::catalog() {
+            for rule in sub_rules {
+                registry.rules.insert(
+                    format!("{}:{}", module, rule),
+                    Claim {
+                        claimant: module.to_string(),
+                        source: Source::Builtin,
+                    },
+                );
+            }
+        }
+        registry
+    }
+
+    /// The built-ins plus everything `config` defines: `[[rules]] prefix`
+    /// entries and custom secret patterns. Errors name both claimants of
+    /// each conflict.
+    pub fn from_config(config: &RevetConfig) -> Result<Self, Vec<String>> {
+        let mut registry = Self::new();
+        let mut errors = Vec::new();
+        for (i, rule) in config.rules.iter().enumerate() {
+            let Some(prefix) = rule.prefix.as_deref() else {
+                continue;
+            };
+            let claimant = match rule.id.as_deref() {
+                Some(id) => format!("[[rules]] {:?}", id),
+                None => format!("[[rules]] entry {}", i),
+            };
+            if let Err(e) = registry.claim_prefix(prefix, &claimant, Source::Custom) {
+                errors.push(e);
+            }
+        }
+        for pattern in &config.analyzers.secrets.patterns {
+            let rule = secret_exposure::custom_rule(&pattern.name);
+            let claimant = format!("[[analyzers.secrets.patterns]] {:?}", pattern.name);
+            if let Err(e) = registry.claim_rule("security", &rule, &claimant, Source::Custom) {
+                errors.push(e);
+            }
+        }
+        if errors.is_empty() {
+            Ok(registry)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Claim `prefix` for `claimant`. Custom prefixes must be upper-case
+    /// letters and digits and not reserved; plugin prefixes must be
+    /// `PLUGIN-<name>`. Claiming a prefix again is fine for the same
+    /// claimant, and for custom rules, which all share their prefixes.
+    pub fn claim_prefix(
+        &mut self,
+        prefix: &str,
+        claimant: &str,
+        source: Source,
+    ) -> Result<(), String> {
+        match &source {
+            Source::Builtin => {}
+            Source::Custom => check_custom_prefix(prefix)
+                .map_err(|e| format!("{} prefix {:?}: {}", claimant, prefix, e))?,
+            Source::Plugin(name) => {
+                let expected = format!("{}-{}", PLUGIN, name);
+                if prefix != expected {
+                    return Err(format!(
+                        "{} prefix {:?}: plugins must use {:?}",
+                        claimant, prefix, expected
+                    ));
+                }
+            }
+        }
+        let claim = Claim {
+            claimant: claimant.to_string(),
+            source,
+        };
+        match self.prefixes.get(prefix) {
+            None => {
+                self.prefixes.insert(prefix.to_string(), claim);
+                Ok(())
+            }
+            Some(existing) if existing.compatible(&claim) => Ok(()),
+            Some(existing) => Err(format!(
+                "finding prefix {:?} is claimed by both {} and {}",
+                prefix, existing, claim
+            )),
+        }
+    }
+
+    /// Claim sub-rule `rule` of `module` for `claimant`. As for prefixes,
+    /// custom claims never conflict with each other (`SecretsConfig::check`
+    /// reports duplicate pattern names).
+    pub fn claim_rule(
+        &mut self,
+        module: &str,
+        rule: &str,
+        claimant: &str,
+        source: Source,
+    ) -> Result<(), String> {
+        let id = format!("{}:{}", module, rule);
+        let claim = Claim {
+            claimant: claimant.to_string(),
+            source,
+        };
+        match self.rules.get(&id) {
+            None => {
+                self.rules.insert(id, claim);
+                Ok(())
+            }
+            Some(existing) if existing.compatible(&claim) => Ok(()),
+            Some(existing) => Err(format!(
+                "sub-rule {:?} is claimed by both {} and {}",
+                id, existing, claim
+            )),
+        }
+    }
+
+    /// Claimant of `prefix`
+    pub fn get(&self, prefix: &str) -> Option<&Claim> {
+        self.prefixes.get(prefix)
+    }
+
+    /// Claimant of the prefix of finding ID `id`
+    pub fn resolve(&self, id: &str) -> Option<&Claim> {
+        self.get(prefix_of(id))
+    }
+
+    /// Claimant of the qualified sub-rule `module:rule`
+    pub fn rule(&self, rule: &str) -> Option<&Claim> {
+        self.rules.get(rule)
+    }
+
+    /// Every claimed prefix, sorted
+    pub fn prefixes(&self) -> impl Iterator<Item = (&str, &Claim)> + '_ {
+        self.prefixes.iter().map(|(p, c)| (p.as_str(), c))
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether `prefix` may be set with `[[rules]] prefix`
+pub fn check_custom_prefix(prefix: &str) -> Result<(), String> {
+    let well_formed = prefix.starts_with(|c: char| c.is_ascii_uppercase())
+        && prefix
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    if !well_formed {
+        Err("must be upper-case letters and digits, starting with a letter".to_string())
+    } else if prefix.starts_with(PLUGIN) {
+        Err(format!("{}… is reserved for plugins", PLUGIN))
+    } else if let Some(builtin) = BUILTIN.iter().find(|b| b.prefix == prefix) {
+        Err(format!("is reserved for {} (builtin)", builtin.name))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+//! Repository analyzer — properties of the repository as a whole
+//!
+//! Every finding is repository-level: it has no file or line (see
+//! [`Finding::location`](crate::finding::Finding::location)). Sub-rules:
+//!
+//! - `license`: no `LICENSE`, `LICENCE` or `COPYING` file at the root
+//! - `stale_baseline`: the baseline holds `stale_baseline_entries` or more
+//!   entries and was created `stale_baseline_days` or more days ago, so it
+//!   has not shrunk by being re-created since
+//! - `no_security`: `[modules] security` is off, so none of the security
+//!   analyzers run
+//! - `branch_protection`: expectations declared in `[analyzers.repository]`
+//!   that the tree does not meet — a `protected_branch` without a
+//!   CODEOWNERS file, or a `required_checks` entry that is not a job of any
+//!   workflow in `.github/workflows`. Nothing is asked of the hosting API.
+//!
+//! Finding prefix: `REPO-`
+
+use crate::analyzer::{catalog_finding, Analyzer};
+use crate::baseline::Baseline;
+use crate::config::{RepositoryConfig, RevetConfig};
+use crate::finding::{Finding, Severity};
+use crate::i18n::Msg;
+use crate::msg;
+use crate::storage::StoragePaths;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Module name of the repository analyzer
+pub const MODULE: &str = "repository";
+
+/// Sub-rules of the repository analyzer
+pub const RULES: &[&str] = &[
+    "license",
+    "stale_baseline",
+    "no_security",
+    "branch_protection",
+];
+
+/// File names (before any extension) that state a license
+const LICENSE_NAMES: &[&str] = &["license", "licence", "copying", "unlicense"];
+
+/// Where GitHub and GitLab look for a CODEOWNERS file
+const CODEOWNERS_PATHS: &[&str] = &["CODEOWNERS", ".github/CODEOWNERS", "docs/CODEOWNERS"];
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Analyzer that reports repository-level findings
+pub struct RepositoryAnalyzer {
+    /// The configuration the review runs with: `[analyzers.repository]`,
+    /// the security switch and where the baseline is kept
+    config: RevetConfig,
+}
+
+impl RepositoryAnalyzer {
+    /// Create a repository analyzer with the default settings
+    pub fn new() -> Self {
+        Self::with_config(&RevetConfig::default())
+    }
+
+    /// Create a repository analyzer honouring `[analyzers.repository]`
+    pub fn with_config(config: &RevetConfig) -> Self {
+        Self {
+            config: config.clone(),
+        }
+    }
+
+    fn settings(&self) -> &RepositoryConfig {
+        &self.config.analyzers.repository
+    }
+
+    fn check_license(&self, repo_root: &Path) -> Option<Finding> {
+        let entries = std::fs::read_dir(repo_root).ok()?;
+        let licensed = entries.flatten().any(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            let stem = name.split('.').next().unwrap_or_default();
+            let stem = stem.split(['-', '_']).next().unwrap_or_default();
+            LICENSE_NAMES.contains(&stem) && entry.path().is_file()
+        });
+        (!licensed).then(|| {
+            finding(
+                "license",
+                Severity::Warning,
+                msg!("repository-license"),
+                msg!("repository-license.suggestion"),
+            )
+        })
+    }
+
+    fn check_baseline(&self, repo_root: &Path) -> Option<Finding> {
+        let paths = StoragePaths::resolve(repo_root, &self.config);
+        let baseline = Baseline::load_at(&paths).ok()??;
+        let settings = self.settings();
+        let entries = baseline.entries.len();
+        let created: u64 = baseline.created_at.parse().ok()?;
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok()?
+            .as_secs();
+        let days = now.saturating_sub(created) / SECONDS_PER_DAY;
+        (entries >= settings.stale_baseline_entries && days >= settings.stale_baseline_days).then(
+            || {
+                finding(
+                    "stale_baseline",
+                    Severity::Warning,
+                    msg!("repository-stale-baseline", entries = entries, days = days),
+                    msg!("repository-stale-baseline.suggestion"),
+                )
+            },
+        )
+    }
+
+    fn check_security(&self) -> Option<Finding> {
+        (!self.config.modules.security).then(|| {
+            finding(
+                "no_security",
+                Severity::Warning,
+                msg!("repository-no-security"),
+                msg!("repository-no-security.suggestion"),
+            )
+        })
+    }
+
+    fn check_branch_protection(&self, repo_root: &Path) -> Vec<Finding> {
+        let settings = self.settings();
+        let mut findings = Vec::new();
+        if let Some(branch) = &settings.protected_branch {
+            if !CODEOWNERS_PATHS.iter().any(|p| repo_root.join(p).is_file()) {
+                findings.push(finding(
+                    "branch_protection",
+                    Severity::Warning,
+                    msg!("repository-codeowners", branch = branch),
+                    msg!("repository-codeowners.suggestion", branch = branch),
+                ));
+            }
+        }
+        if settings.required_checks.is_empty() {
+            return findings;
+        }
+        let jobs = workflow_jobs(repo_root);
+        for check in &settings.required_checks {
+            // A check shown as `Workflow / job` names the job last
+            let job = check.rsplit(" / ").next().unwrap_or(check).trim();
+            if !jobs.iter().any(|j| j == job) {
+                findings.push(finding(
+                    "branch_protection",
+                    Severity::Warning,
+                    msg!("repository-required-check", check = check),
+                    msg!("repository-required-check.suggestion"),
+                ));
+            }
+        }
+        findings
+    }
+}
+
+/// A repository-level finding of sub-rule `rule`
+fn finding(rule: &str, severity: Severity, message: Msg, suggestion: Msg) -> Finding {
+    Finding {
+        rule: Some(rule.to_string()),
+        ..catalog_finding(severity, message, PathBuf::new(), 0, Some(suggestion), None)
+    }
+}
+
+/// Ids and `name`s of the jobs of every workflow in `.github/workflows`
+fn workflow_jobs(repo_root: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(repo_root.join(".github/workflows")) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.extension()
+                .is_some_and(|ext| ext == "yml" || ext == "yaml")
+        })
+        .filter_map(|p| std::fs::read_to_string(p).ok())
+        .flat_map(|content| jobs_of(&content))
+        .collect()
+}
+
+/// Ids and `name`s of the jobs under the top-level `jobs:` key of a
+/// workflow, read by indentation
+fn jobs_of(content: &str) -> Vec<String> {
+    let mut jobs = Vec::new();
+    let mut in_jobs = false;
+    let mut job_indent = None;
+    let mut field_indent = None;
+    for line in content.lines() {
+        let text = line.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if indent == 0 {
+            in_jobs = text == "jobs:";
+            job_indent = None;
+            continue;
+        }
+        if !in_jobs {
+            continue;
+        }
+        let job = *job_indent.get_or_insert(indent);
+        if indent == job {
+            field_indent = None;
+            if let Some(id) = text.strip_suffix(':') {
+                jobs.push(unquote(id).to_string());
+            }
+        } else if indent > job && *field_indent.get_or_insert(indent) == indent {
+            if let Some(name) = text.strip_prefix("name:") {
+                jobs.push(unquote(name.trim()).to_string());
+            }
+        }
+    }
+    jobs
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim_matches(|c| c == '"' || c == '\'')
+}
+
+impl Default for RepositoryAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer for RepositoryAnalyzer {
+    fn name(&self) -> &str {
+        "Repository"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "REPO"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.repository
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some(MODULE)
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn analyze_files(&self, _files: &[PathBuf], repo_root: &Path) -> Vec<Finding> {
+        let mut findings: Vec<Finding> = [
+            self.check_license(repo_root),
+            self.check_baseline(repo_root),
+            self.check_security(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        findings.extend(self.check_branch_protection(repo_root));
+        findings
+    }
+}
@@ -0,0 +1,919 @@
+//! Resource leak analyzer — unclosed JVM resources and multi-write methods
+//! without a transaction
+//!
+//! Java and Kotlin method bodies are parsed with tree-sitter. Two patterns
+//! are flagged:
+//!
+//! - **Unclosed resources** — a `Connection`, `InputStream`, `Cursor`, … is
+//!   created (`new`, a constructor call, or a factory such as
+//!   `getConnection()`) and is neither opened in try-with-resources or a
+//!   `use {}` block, returned, stored in a field, handed to a wrapping
+//!   constructor, nor closed in a `finally` block.
+//! - **Writes without a transaction** — a method makes two or more
+//!   `save`/`update`/`delete` calls on repository-like receivers
+//!   (`orderRepository`, `userDao`, `entityManager`) without `@Transactional`
+//!   or Room's `@Transaction` on the method or an enclosing class. Writes
+//!   inside a `transaction { }` / `transactionTemplate.execute(...)` callback
+//!   are not counted.
+//!
+//! Extra closeable type names come from `[resource_leaks] closeable_types`.
+//! Java files whose target predates Java 7 (see [`crate::language_target`])
+//! are told to close resources in `finally` rather than try-with-resources.
+//!
+//! Disabled by default (`modules.resource_leaks = false`).
+
+use crate::analyzer::{make_finding, Analyzer};
+use crate::config::RevetConfig;
+use crate::finding::{Finding, FixKind, Severity};
+use crate::language_target::{Language, LanguageTargets};
+use crate::parser::grammar::{language_for, Family};
+use crate::parser::{java, kotlin};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use tree_sitter::Node;
+
+// ── Tables ────────────────────────────────────────────────────────────────────
+
+/// Types whose instances must be closed by whoever creates them
+const CLOSEABLE_TYPES: &[&str] = &[
+    "Connection",
+    "Statement",
+    "PreparedStatement",
+    "CallableStatement",
+    "InputStream",
+    "OutputStream",
+    "FileInputStream",
+    "FileOutputStream",
+    "BufferedInputStream",
+    "BufferedOutputStream",
+    "DataInputStream",
+    "DataOutputStream",
+    "ObjectInputStream",
+    "ObjectOutputStream",
+    "ZipInputStream",
+    "ZipOutputStream",
+    "GZIPInputStream",
+    "GZIPOutputStream",
+    "Reader",
+    "Writer",
+    "FileReader",
+    "FileWriter",
+    "BufferedReader",
+    "BufferedWriter",
+    "InputStreamReader",
+    "OutputStreamWriter",
+    "PrintWriter",
+    "PrintStream",
+    "RandomAccessFile",
+    "FileChannel",
+    "Scanner",
+    "Socket",
+    "ServerSocket",
+    "DatagramSocket",
+    "ZipFile",
+    "JarFile",
+    "Cursor",
+];
+
+/// Factory methods whose result the caller owns, with the type they return
+const FACTORIES: &[(&str, &str)] = &[
+    ("getConnection", "Connection"),
+    ("createStatement", "Statement"),
+    ("prepareStatement", "PreparedStatement"),
+    ("prepareCall", "CallableStatement"),
+    ("newInputStream", "InputStream"),
+    ("newOutputStream", "OutputStream"),
+    ("newBufferedReader", "BufferedReader"),
+    ("newBufferedWriter", "BufferedWriter"),
+    ("openStream", "InputStream"),
+    ("openInputStream", "InputStream"),
+    ("openOutputStream", "OutputStream"),
+    ("rawQuery", "Cursor"),
+    // Kotlin stdlib extensions
+    ("inputStream", "InputStream"),
+    ("outputStream", "OutputStream"),
+    ("bufferedReader", "BufferedReader"),
+    ("bufferedWriter", "BufferedWriter"),
+    ("printWriter", "PrintWriter"),
+];
+
+/// Accessors returning a resource owned by their receiver — closing the
+/// socket or process closes the stream, so the caller need not
+const BORROWED: &[&str] = &[
+    "getInputStream",
+    "getOutputStream",
+    "getErrorStream",
+    "getChannel",
+];
+
+/// Calls that release a resource when made in a `finally` block
+const CLOSE_CALLS: &[&str] = &["close", "closeQuietly", "closeSilently"];
+
+/// Repository methods that write
+const MUTATIONS: &[&str] = &[
+    "save",
+    "saveAll",
+    "saveAndFlush",
+    "saveAllAndFlush",
+    "insert",
+    "insertAll",
+    "update",
+    "updateAll",
+    "upsert",
+    "delete",
+    "deleteAll",
+    "deleteById",
+    "deleteAllById",
+    "deleteInBatch",
+    "remove",
+    "persist",
+    "merge",
+];
+
+/// Receiver name suffixes (lowercased) that mark a repository
+const REPOSITORY_SUFFIXES: &[&str] = &["repository", "repo", "dao", "entitymanager"];
+
+/// Annotations that make a method transactional
+const TRANSACTION_ANNOTATIONS: &[&str] = &["Transactional", "Transaction"];
+
+// ── Language ──────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lang {
+    Java,
+    Kotlin,
+}
+
+impl Lang {
+    fn of(path: &Path) -> Option<Self> {
+        match Family::of(path)? {
+            Family::Java => Some(Self::Java),
+            Family::Kotlin => Some(Self::Kotlin),
+            _ => None,
+        }
+    }
+
+    fn is_method(self, kind: &str) -> bool {
+        match self {
+            Self::Java => matches!(kind, "method_declaration" | "constructor_declaration"),
+            Self::Kotlin => kind == "function_declaration",
+        }
+    }
+
+    fn is_class(self, kind: &str) -> bool {
+        match self {
+            Self::Java => matches!(
+                kind,
+                "class_declaration"
+                    | "interface_declaration"
+                    | "enum_declaration"
+                    | "record_declaration"
+            ),
+            Self::Kotlin => matches!(kind, "class_declaration" | "object_declaration"),
+        }
+    }
+
+    fn annotations(self, node: &Node, source: &str) -> Vec<String> {
+        match self {
+            Self::Java => java::extract_annotations(node, source),
+            Self::Kotlin => kotlin::extract_annotations(node, source),
+        }
+    }
+
+    fn finally_kind(self) -> &'static str {
+        match self {
+            Self::Java => "finally_clause",
+            Self::Kotlin => "finally_block",
+        }
+    }
+}
+
+// ── Analyzer ──────────────────────────────────────────────────────────────────
+
+pub struct ResourceLeaksAnalyzer {
+    closeable_types: HashSet<String>,
+    /// `[targets]`
+    targets: BTreeMap<String, String>,
+}
+
+impl ResourceLeaksAnalyzer {
+    pub fn new() -> Self {
+        Self::with_types(&[])
+    }
+
+    /// Create an analyzer that also treats `extra` type names as closeable
+    pub fn with_types(extra: &[String]) -> Self {
+        let closeable_types = CLOSEABLE_TYPES
+            .iter()
+            .map(|t| t.to_string())
+            .chain(extra.iter().map(|t| simple_type_name(t).to_string()))
+            .collect();
+        Self {
+            closeable_types,
+            targets: BTreeMap::new(),
+        }
+    }
+
+    /// Take the language targets `[targets]` sets over manifests
+    pub fn with_targets(mut self, targets: &BTreeMap<String, String>) -> Self {
+        self.targets = targets.clone();
+        self
+    }
+
+    fn scan_file(&self, path: &Path, targets: &mut LanguageTargets) -> Vec<Finding> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => self.scan_source(path, &content, targets),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn scan_source(
+        &self,
+        path: &Path,
+        content: &str,
+        targets: &mut LanguageTargets,
+    ) -> Vec<Finding> {
+        let Some(lang) = Lang::of(path) else {
+            return Vec::new();
+        };
+        let Some(tree) = language_for(path).and_then(|g| g.parse(content)) else {
+            return Vec::new();
+        };
+
+        let mut scan = Scan {
+            lang,
+            source: content,
+            path,
+            closeable_types: &self.closeable_types,
+            try_with_resources: lang != Lang::Java
+                || targets.for_file(Language::Java, path).at_least("7"),
+            findings: Vec::new(),
+        };
+        scan.visit(tree.root_node(), &mut Vec::new());
+        scan.findings
+    }
+}
+
+impl Default for ResourceLeaksAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer for ResourceLeaksAnalyzer {
+    fn name(&self) -> &str {
+        "Resource Leaks"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "RES"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.resource_leaks
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some("resource_leaks")
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Lang::of(path).is_some()
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], repo_root: &Path) -> Vec<Finding> {
+        let mut targets = LanguageTargets::new(repo_root, &self.targets);
+        files
+            .iter()
+            .filter(|f| Lang::of(f).is_some())
+            .flat_map(|f| self.scan_file(f, &mut targets))
+            .collect()
+    }
+
+    fn analyze_source(&self, path: &Path, content: &str, repo_root: &Path) -> Vec<Finding> {
+        let mut targets = LanguageTargets::new(repo_root, &self.targets);
+        self.scan_source(path, content, &mut targets)
+    }
+}
+
+// ── Per-file scan ─────────────────────────────────────────────────────────────
+
+/// What happens to a freshly created resource
+enum Fate {
+    /// Closed, returned, stored, or handed to a new owner
+    Owned,
+    /// Assigned to a local variable; its uses decide
+    Local(String),
+    /// Discarded or only used in place
+    Leaked,
+}
+
+struct Scan<'a> {
+    lang: Lang,
+    source: &'a str,
+    path: &'a Path,
+    closeable_types: &'a HashSet<String>,
+    /// Whether the Java target has try-with-resources (Java 7)
+    try_with_resources: bool,
+    findings: Vec<Finding>,
+}
+
+impl<'a> Scan<'a> {
+    /// Walk declarations, tracking annotations of enclosing classes
+    fn visit(&mut self, node: Node, class_annotations: &mut Vec<String>) {
+        let kind = node.kind();
+        let is_class = self.lang.is_class(kind);
+        let pushed = if is_class {
+            let annotations = self.lang.annotations(&node, self.source);
+            let n = annotations.len();
+            class_annotations.extend(annotations);
+            n
+        } else {
+            0
+        };
+
+        if self.lang.is_method(kind) {
+            self.check_method(node, class_annotations);
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.visit(child, class_annotations);
+        }
+
+        class_annotations.truncate(class_annotations.len() - pushed);
+    }
+
+    fn check_method(&mut self, method: Node, class_annotations: &[String]) {
+        let body = match self.lang {
+            Lang::Java => method.child_by_field_name("body"),
+            Lang::Kotlin => child_of_kind(&method, "function_body"),
+        };
+        let Some(body) = body else {
+            return;
+        };
+
+        let nodes = self.body_nodes(body);
+        let locals = self.locals(method, &nodes);
+        self.check_resources(&nodes, &locals);
+        self.check_transaction(method, &nodes, class_annotations);
+    }
+
+    /// Every node of a method body, excluding nested classes and functions
+    fn body_nodes(&self, body: Node<'a>) -> Vec<Node<'a>> {
+        let mut nodes = Vec::new();
+        let mut stack = vec![body];
+        while let Some(node) = stack.pop() {
+            nodes.push(node);
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                let kind = child.kind();
+                if kind == "class_body" || self.lang.is_method(kind) || self.lang.is_class(kind) {
+                    continue;
+                }
+                stack.push(child);
+            }
+        }
+        nodes.sort_by_key(|n| n.start_byte());
+        nodes
+    }
+
+    /// Parameter and local variable names of a method
+    fn locals(&self, method: Node, nodes: &[Node]) -> HashSet<String> {
+        let mut locals = HashSet::new();
+        match self.lang {
+            Lang::Java => {
+                if let Some(params) = method.child_by_field_name("parameters") {
+                    let mut cursor = params.walk();
+                    for param in params.named_children(&mut cursor) {
+                        if let Some(name) = self.field_text(&param, "name") {
+                            locals.insert(name.to_string());
+                        }
+                    }
+                }
+                for node in nodes {
+                    if node.kind() == "variable_declarator"
+                        && node.parent().map(|p| p.kind()) == Some("local_variable_declaration")
+                    {
+                        if let Some(name) = self.field_text(node, "name") {
+                            locals.insert(name.to_string());
+                        }
+                    }
+                }
+            }
+            Lang::Kotlin => {
+                if let Some(params) = child_of_kind(&method, "function_value_parameters") {
+                    let mut cursor = params.walk();
+                    for param in params.named_children(&mut cursor) {
+                        if let Some(name) = child_of_kind(&param, "identifier") {
+                            locals.insert(self.text(&name).to_string());
+                        }
+                    }
+                }
+                for node in nodes {
+                    if node.kind() == "property_declaration" {
+                        if let Some(name) = self.kotlin_property_name(node) {
+                            locals.insert(name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        locals
+    }
+
+    // ── Unclosed resources ───────────────────────────────────────────────────
+
+    fn check_resources(&mut self, nodes: &[Node], locals: &HashSet<String>) {
+        let mut reported = HashSet::new();
+        for node in nodes {
+            let Some(ty) = self.created_type(node) else {
+                continue;
+            };
+            let leaked_as = match self.fate(node, locals) {
+                Fate::Owned => continue,
+                Fate::Local(name) if self.escapes(&name, nodes, locals) => continue,
+                Fate::Local(name) => Some(name),
+                Fate::Leaked => None,
+            };
+
+            let line = node.start_position().row + 1;
+            if !reported.insert(line) {
+                continue;
+            }
+            let suggestion = self.resource_suggestion(node, &ty, leaked_as.as_deref());
+            self.findings.push(make_finding(
+                Severity::Warning,
+                format!("`{}` is not closed on all paths", ty),
+                self.path.to_path_buf(),
+                line,
+                Some(suggestion),
+                Some(FixKind::Suggestion),
+            ));
+        }
+    }
+
+    /// Closeable type produced by `node`, if it creates a resource
+    fn created_type(&self, node: &Node) -> Option<String> {
+        match (self.lang, node.kind()) {
+            (Lang::Java, "object_creation_expression") => {
+                // Anonymous subclasses are not resources we can track
+                if child_of_kind(node, "class_body").is_some() {
+                    return None;
+                }
+                let ty = simple_type_name(self.field_text(node, "type")?);
+                self.closeable_types.contains(ty).then(|| ty.to_string())
+            }
+            (Lang::Java, "method_invocation") => {
+                let name = self.field_text(node, "name")?;
+                if let Some(ty) = factory_type(name) {
+                    return Some(ty.to_string());
+                }
+                if BORROWED.contains(&name) {
+                    return None;
+                }
+                // `Connection c = pool.borrow();` — typed by its declaration
+                let declarator = node
+                    .parent()
+                    .filter(|p| p.kind() == "variable_declarator")?;
+                let declaration = declarator
+                    .parent()
+                    .filter(|p| p.kind() == "local_variable_declaration")?;
+                let ty = simple_type_name(self.field_text(&declaration, "type")?);
+                self.closeable_types.contains(ty).then(|| ty.to_string())
+            }
+            (Lang::Kotlin, "call_expression") => {
+                let callee = node.named_child(0)?;
+                match callee.kind() {
+                    "identifier" => {
+                        let name = self.text(&callee);
+                        self.closeable_types
+                            .contains(name)
+                            .then(|| name.to_string())
+                    }
+                    "navigation_expression" => {
+                        let name = self.text(&callee.named_child(callee.named_child_count() - 1)?);
+                        if let Some(ty) = factory_type(name) {
+                            return Some(ty.to_string());
+                        }
+                        if BORROWED.contains(&name) {
+                            return None;
+                        }
+                        // `val c: Cursor = db.query(...)`
+                        let property = node
+                            .parent()
+                            .filter(|p| p.kind() == "property_declaration")?;
+                        let declared = child_of_kind(&property, "variable_declaration")
+                            .and_then(|v| child_of_kind(&v, "user_type"))?;
+                        let ty = simple_type_name(self.text(&declared));
+                        self.closeable_types.contains(ty).then(|| ty.to_string())
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Where a created resource ends up
+    fn fate(&self, node: &Node, locals: &HashSet<String>) -> Fate {
+        let (expr, Some(parent)) = self.unwrap_parens(*node) else {
+            return Fate::Owned;
+        };
+        match self.lang {
+            Lang::Java => match parent.kind() {
+                "resource" => Fate::Owned,
+                "variable_declarator" => {
+                    match (
+                        parent.parent().map(|p| p.kind()),
+                        self.field_text(&parent, "name"),
+                    ) {
+                        (Some("local_variable_declaration"), Some(name)) => {
+                            Fate::Local(name.to_string())
+                        }
+                        _ => Fate::Owned,
+                    }
+                }
+                "assignment_expression" => self.assigned_to(&parent, locals),
+                "method_invocation" if is_same(parent.child_by_field_name("object"), &expr) => {
+                    Fate::Leaked
+                }
+                "argument_list" => match parent.parent().map(|p| p.kind()) {
+                    Some("method_invocation") => Fate::Leaked,
+                    _ => Fate::Owned,
+                },
+                "expression_statement" => Fate::Leaked,
+                _ => Fate::Owned,
+            },
+            Lang::Kotlin => match parent.kind() {
+                "navigation_expression" if is_same(parent.named_child(0), &expr) => {
+                    let call = parent
+                        .parent()
+                        .filter(|c| c.kind() == "call_expression")
+                        .filter(|c| is_same(c.named_child(0), &parent));
+                    let method = parent
+                        .named_child(parent.named_child_count() - 1)
+                        .map(|m| self.text(&m));
+                    match (call, method) {
+                        (Some(_), Some("use")) => Fate::Owned,
+                        // Wrapped, e.g. `FileInputStream(f).bufferedReader()`;
+                        // the wrapper is checked on its own
+                        (Some(_), Some(m)) if factory_type(m).is_some() => Fate::Owned,
+                        _ => Fate::Leaked,
+                    }
+                }
+                "property_declaration" => match parent.parent().map(|p| p.kind()) {
+                    Some("class_body") | Some("source_file") | None => Fate::Owned,
+                    _ => self
+                        .kotlin_property_name(&parent)
+                        .map_or(Fate::Owned, |n| Fate::Local(n.to_string())),
+                },
+                "assignment" => self.assigned_to(&parent, locals),
+                "value_argument" => {
+                    let call = parent.parent().and_then(|args| args.parent());
+                    match call {
+                        Some(call) if self.is_kotlin_constructor_call(&call) => Fate::Owned,
+                        Some(call) if call.kind() == "call_expression" => Fate::Leaked,
+                        _ => Fate::Owned,
+                    }
+                }
+                "block" | "lambda_literal" => {
+                    // The last expression of a lambda or block may be its value
+                    let last = parent.named_child(parent.named_child_count() - 1);
+                    if is_same(last, &expr) {
+                        Fate::Owned
+                    } else {
+                        Fate::Leaked
+                    }
+                }
+                _ => Fate::Owned,
+            },
+        }
+    }
+
+    /// Fate of a resource on the right of `left = resource`
+    fn assigned_to(&self, assignment: &Node, locals: &HashSet<String>) -> Fate {
+        match assignment.child_by_field_name("left") {
+            Some(left) if left.kind() == "identifier" && locals.contains(self.text(&left)) => {
+                Fate::Local(self.text(&left).to_string())
+            }
+            // Stored in a field or property
+            _ => Fate::Owned,
+        }
+    }
+
+    /// Whether any use of local `name` closes or hands off the resource
+    fn escapes(&self, name: &str, nodes: &[Node], locals: &HashSet<String>) -> bool {
+        nodes
+            .iter()
+            .filter(|n| n.kind() == "identifier" && self.text(n) == name)
+            .any(|id| self.use_escapes(id, locals))
+    }
+
+    fn use_escapes(&self, id: &Node, locals: &HashSet<String>) -> bool {
+        let mut in_finally = false;
+        let mut ancestor = id.parent();
+        while let Some(a) = ancestor {
+            if a.kind() == self.lang.finally_kind() {
+                in_finally = true;
+            } else if self.lang.is_method(a.kind()) {
+                break;
+            }
+            ancestor = a.parent();
+        }
+
+        let (expr, Some(parent)) = self.unwrap_parens(*id) else {
+            return false;
+        };
+        match (self.lang, parent.kind()) {
+            // Java 9 `try (conn) { ... }`, `return conn;`, `fun open() = stream`
+            (Lang::Java, "resource") | (_, "return_statement") | (_, "return_expression") => true,
+            (Lang::Kotlin, "function_body") => true,
+            (_, "assignment_expression") | (_, "assignment") => {
+                is_same(parent.child_by_field_name("right"), &expr)
+                    && matches!(self.assigned_to(&parent, locals), Fate::Owned)
+            }
+            (Lang::Java, "method_invocation") => {
+                is_same(parent.child_by_field_name("object"), &expr)
+                    && in_finally
+                    && self
+                        .field_text(&parent, "name")
+                        .is_some_and(|m| CLOSE_CALLS.contains(&m))
+            }
+            (Lang::Java, "argument_list") => match parent.parent() {
+                Some(call) if call.kind() == "object_creation_expression" => true,
+                Some(call) if call.kind() == "method_invocation" => {
+                    in_finally
+                        && self
+                            .field_text(&call, "name")
+                            .is_some_and(|m| CLOSE_CALLS.contains(&m))
+                }
+                _ => false,
+            },
+            (Lang::Kotlin, "navigation_expression") => {
+                if !is_same(parent.named_child(0), &expr) {
+                    return false;
+                }
+                let method = parent
+                    .named_child(parent.named_child_count() - 1)
+                    .map(|m| self.text(&m))
+                    .unwrap_or("");
+                method == "use"
+                    || factory_type(method).is_some()
+                    || (in_finally && CLOSE_CALLS.contains(&method))
+            }
+            (Lang::Kotlin, "value_argument") => {
+                match parent.parent().and_then(|args| args.parent()) {
+                    Some(call) if self.is_kotlin_constructor_call(&call) => true,
+                    Some(call) => {
+                        in_finally
+                            && call
+                                .named_child(0)
+                                .is_some_and(|c| CLOSE_CALLS.contains(&self.text(&c)))
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn resource_suggestion(&self, node: &Node, ty: &str, local: Option<&str>) -> String {
+        let expr = collapse_whitespace(self.text(node));
+        let var = local.map_or_else(|| lower_camel(ty), str::to_string);
+        match self.lang {
+            Lang::Java => {
+                // Keep the declared type when the resource came from a declaration
+                let declared = node
+                    .parent()
+                    .filter(|p| p.kind() == "variable_declarator")
+                    .and_then(|d| d.parent())
+                    .and_then(|d| self.field_text(&d, "type"))
+                    .unwrap_or(ty);
+                if self.try_with_resources {
+                    format!(
+                        "Use try-with-resources: `try ({} {} = {}) {{ ... }}`",
+                        declared, var, expr
+                    )
+                } else {
+                    format!(
+                        "Close it in a `finally` block: `{} {} = {}; try {{ ... }} finally {{ {}.close(); }}`",
+                        declared, var, expr, var
+                    )
+                }
+            }
+            Lang::Kotlin => format!("Use a `use` block: `{}.use {{ {} -> ... }}`", expr, var),
+        }
+    }
+
+    // ── Writes without a transaction ─────────────────────────────────────────
+
+    fn check_transaction(&mut self, method: Node, nodes: &[Node], class_annotations: &[String]) {
+        let writes: Vec<String> = nodes
+            .iter()
+            .filter_map(|n| self.repository_write(n))
+            .filter(|(call, _)| !self.in_transaction_callback(call))
+            .map(|(_, label)| label)
+            .collect();
+        if writes.len() < 2 {
+            return;
+        }
+
+        let annotated = self
+            .lang
+            .annotations(&method, self.source)
+            .iter()
+            .chain(class_annotations)
+            .any(|a| TRANSACTION_ANNOTATIONS.contains(&simple_type_name(a)));
+        if annotated {
+            return;
+        }
+
+        let name_node = method.child_by_field_name("name").unwrap_or(method);
+        let name = self.text(&name_node);
+        let suggestion = match self.lang {
+            Lang::Java => format!(
+                "Annotate `{}` (or its class) with `@Transactional` so the writes commit or roll back together",
+                name
+            ),
+            Lang::Kotlin => format!(
+                "Annotate `{}` (or its class) with `@Transactional` (`@Transaction` for Room DAOs) so the writes commit or roll back together",
+                name
+            ),
+        };
+        self.findings.push(make_finding(
+            Severity::Warning,
+            format!(
+                "`{}` makes {} repository writes without a transaction ({})",
+                name,
+                writes.len(),
+                writes.join(", ")
+            ),
+            self.path.to_path_buf(),
+            name_node.start_position().row + 1,
+            Some(suggestion),
+            Some(FixKind::Suggestion),
+        ));
+    }
+
+    /// `orderRepository.save` for a write call on a repository-like receiver
+    fn repository_write<'n>(&self, node: &Node<'n>) -> Option<(Node<'n>, String)> {
+        let (receiver, method) = match (self.lang, node.kind()) {
+            (Lang::Java, "method_invocation") => {
+                let object = node.child_by_field_name("object")?;
+                let receiver = match object.kind() {
+                    "identifier" => object,
+                    "field_access" => object.child_by_field_name("field")?,
+                    _ => return None,
+                };
+                (receiver, node.child_by_field_name("name")?)
+            }
+            (Lang::Kotlin, "call_expression") => {
+                let callee = node
+                    .named_child(0)
+                    .filter(|c| c.kind() == "navigation_expression")?;
+                let object = callee.named_child(0)?;
+                let receiver = match object.kind() {
+                    "identifier" => object,
+                    "navigation_expression" => {
+                        object.named_child(object.named_child_count() - 1)?
+                    }
+                    _ => return None,
+                };
+                (
+                    receiver,
+                    callee.named_child(callee.named_child_count() - 1)?,
+                )
+            }
+            _ => return None,
+        };
+
+        let receiver = self.text(&receiver);
+        let method = self.text(&method);
+        let lower = receiver.to_lowercase();
+        let is_repository = REPOSITORY_SUFFIXES.iter().any(|s| lower.ends_with(s));
+        (is_repository && MUTATIONS.contains(&method))
+            .then(|| (*node, format!("{}.{}", receiver, method)))
+    }
+
+    /// Whether `call` runs inside a callback passed to a transaction helper
+    /// (`transaction { }`, `transactionTemplate.execute(...)`)
+    fn in_transaction_callback(&self, call: &Node) -> bool {
+        let mut child = *call;
+        while let Some(a) = child.parent() {
+            if self.lang.is_method(a.kind()) {
+                return false;
+            }
+            let in_arguments = matches!(
+                child.kind(),
+                "argument_list" | "value_arguments" | "annotated_lambda"
+            );
+            if in_arguments
+                && matches!(a.kind(), "method_invocation" | "call_expression")
+                && self.callee_text(&a).to_lowercase().contains("transaction")
+            {
+                return true;
+            }
+            child = a;
+        }
+        false
+    }
+
+    // ── Helpers ──────────────────────────────────────────────────────────────
+
+    fn text(&self, node: &Node) -> &'a str {
+        node.utf8_text(self.source.as_bytes()).unwrap_or("")
+    }
+
+    fn field_text(&self, node: &Node, field: &str) -> Option<&'a str> {
+        node.child_by_field_name(field).map(|n| self.text(&n))
+    }
+
+    /// A call's text up to its arguments (`transactionTemplate.execute`)
+    fn callee_text(&self, call: &Node) -> &'a str {
+        let mut cursor = call.walk();
+        let end = call
+            .children(&mut cursor)
+            .find(|c| {
+                matches!(
+                    c.kind(),
+                    "argument_list" | "value_arguments" | "annotated_lambda"
+                )
+            })
+            .map_or(call.end_byte(), |c| c.start_byte());
+        self.source.get(call.start_byte()..end).unwrap_or("")
+    }
+
+    fn kotlin_property_name(&self, property: &Node) -> Option<&'a str> {
+        let declaration = child_of_kind(property, "variable_declaration")?;
+        Some(self.text(&child_of_kind(&declaration, "identifier")?))
+    }
+
+    /// Kotlin has no `new`: a call to a capitalised name is a constructor
+    fn is_kotlin_constructor_call(&self, call: &Node) -> bool {
+        call.kind() == "call_expression"
+            && call
+                .named_child(0)
+                .filter(|c| c.kind() == "identifier")
+                .is_some_and(|c| {
+                    self.text(&c)
+                        .starts_with(|ch: char| ch.is_ascii_uppercase())
+                })
+    }
+
+    /// The outermost parenthesised / cast expression around `node`, and its parent
+    fn unwrap_parens<'n>(&self, node: Node<'n>) -> (Node<'n>, Option<Node<'n>>) {
+        let mut expr = node;
+        while let Some(parent) = expr.parent() {
+            if matches!(
+                parent.kind(),
+                "parenthesized_expression" | "cast_expression" | "as_expression"
+            ) {
+                expr = parent;
+            } else {
+                return (expr, Some(parent));
+            }
+        }
+        (expr, None)
+    }
+}
+
+fn child_of_kind<'n>(node: &Node<'n>, kind: &str) -> Option<Node<'n>> {
+    let mut cursor = node.walk();
+    let found = node.children(&mut cursor).find(|c| c.kind() == kind);
+    found
+}
+
+fn is_same(a: Option<Node>, b: &Node) -> bool {
+    a.is_some_and(|a| a.id() == b.id())
+}
+
+fn factory_type(method: &str) -> Option<&'static str> {
+    FACTORIES
+        .iter()
+        .find(|(name, _)| *name == method)
+        .map(|(_, ty)| *ty)
+}
+
+/// `java.sql.Connection` → `Connection`, `List<String>` → `List`
+fn simple_type_name(ty: &str) -> &str {
+    let ty = ty.split('<').next().unwrap_or(ty).trim_end_matches('?');
+    ty.rsplit('.').next().unwrap_or(ty).trim()
+}
+
+/// `FileInputStream` → `fileInputStream`
+fn lower_camel(ty: &str) -> String {
+    let mut chars = ty.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
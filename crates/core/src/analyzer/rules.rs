@@ -0,0 +1,195 @@
+//! Sub-rule selection — `--module module:rule` and `[analyzers.<module>]`
+//!
+//! Analyzers tag each finding with the bare id of the sub-rule that produced
+//! it (see [`Analyzer::rules`](super::Analyzer::rules)). The dispatcher
+//! qualifies the tag with the analyzer's module and drops findings whose
+//! sub-rule the config deselects, so every analyzer is filtered the same way.
+
+use crate::config::{ModulesConfig, RevetConfig, RuleSelection};
+use crate::finding::Finding;
+use crate::rollout;
+use crate::trace::{self, Decision};
+use std::collections::BTreeMap;
+
+use super::AnalyzerDispatcher;
+
+/// Sub-rule ids of the built-in analyzers, keyed by module name
+///
+/// Every module in [`ModulesConfig::NAMES`] is present; modules whose
+/// analyzers define no sub-rules map to an empty list.
+pub fn catalog() -> BTreeMap<&'static str, Vec<&'static str>> {
+    let mut catalog: BTreeMap<&'static str, Vec<&'static str>> = ModulesConfig::NAMES
+        .iter()
+        .map(|name| (*name, Vec::new()))
+        .collect();
+    for (module, rules) in AnalyzerDispatcher::new().rules() {
+        catalog.entry(module).or_default().extend_from_slice(rules);
+    }
+    catalog
+        .entry(super::suppressions::MODULE)
+        .or_default()
+        .extend_from_slice(super::suppressions::RULES);
+    catalog
+        .entry(super::refactor::MODULE)
+        .or_default()
+        .extend_from_slice(super::refactor::RULES);
+    catalog
+        .entry(super::config_drift::MODULE)
+        .or_default()
+        .extend_from_slice(super::config_drift::RULES);
+    catalog
+}
+
+/// Check that every name in `rules` is a sub-rule of `module`
+///
+/// The error lists the valid sub-rules, e.g.
+/// `unknown sub-rule "swalowed" for error_handling (valid: empty_catch, ...)`.
+pub fn check<'a>(module: &str, rules: impl IntoIterator<Item = &'a str>) -> Result<(), String> {
+    let catalog = catalog();
+    let Some(valid) = catalog.get(module) else {
+        return Err(format!(
+            "unknown module {:?} (known: {})",
+            module,
+            ModulesConfig::NAMES.join(", ")
+        ));
+    };
+    match rules
+        .into_iter()
+        .find(|rule| !valid.iter().any(|v| v == rule))
+    {
+        None => Ok(()),
+        Some(rule) if valid.is_empty() => Err(format!(
+            "unknown sub-rule {:?} for {} ({} has no sub-rules)",
+            rule, module, module
+        )),
+        Some(rule) => Err(format!(
+            "unknown sub-rule {:?} for {} (valid: {})",
+            rule,
+            module,
+            valid.join(", ")
+        )),
+    }
+}
+
+/// Errors for every `[analyzers.<module>]` table that names an unknown
+/// module or sub-rule (including the selections in `[analyzers.skew]`,
+/// `[analyzers.i18n]`, `[analyzers.feature_flags]`,
+/// `[analyzers.debug_artifacts]`, `[analyzers.test_quality]`,
+/// `[analyzers.copy]`, `[analyzers.numeric]`, `[analyzers.repository]`,
+/// `[analyzers.coupling]`, `[analyzers.docs]` and `[analyzers.concurrency]`),
+/// or whose `rollout_percent` is over 100. The sub-rules of
+/// `[[analyzers.secrets.patterns]]` count as `security`'s.
+pub fn check_selections(config: &RevetConfig) -> Vec<String> {
+    let skew = ("skew", &config.analyzers.skew.selection);
+    let i18n = ("i18n", &config.analyzers.i18n.selection);
+    let complexity = ("complexity", &config.analyzers.complexity.selection);
+    let flags = ("feature_flags", &config.analyzers.feature_flags.selection);
+    let debug = (
+        "debug_artifacts",
+        &config.analyzers.debug_artifacts.selection,
+    );
+    let tests = ("test_quality", &config.analyzers.test_quality.selection);
+    let copy = ("copy", &config.analyzers.copy.selection);
+    let numeric = ("numeric", &config.analyzers.numeric.selection);
+    let websec = ("websec", &config.analyzers.websec.selection);
+    let contract = ("contract", &config.analyzers.contract.selection);
+    let repository = ("repository", &config.analyzers.repository.selection);
+    let coupling = ("coupling", &config.analyzers.coupling.selection);
+    let docs = ("docs", &config.analyzers.docs.selection);
+    let concurrency = ("concurrency", &config.analyzers.concurrency.selection);
+    let custom: Vec<String> = config
+        .analyzers
+        .secrets
+        .patterns
+        .iter()
+        .map(|p| super::secret_exposure::custom_rule(&p.name))
+        .collect();
+    let selections: Vec<(&str, &RuleSelection)> = config
+        .analyzers
+        .rules
+        .iter()
+        .map(|(module, selection)| (module.as_str(), selection))
+        .chain([
+            skew,
+            i18n,
+            complexity,
+            flags,
+            debug,
+            tests,
+            copy,
+            numeric,
+            websec,
+            contract,
+            repository,
+            coupling,
+            docs,
+            concurrency,
+        ])
+        .collect();
+    let mut errors: Vec<String> = selections
+        .iter()
+        .filter_map(|(module, selection)| {
+            let names = selection
+                .names()
+                .filter(|name| *module != "security" || !custom.iter().any(|c| c == name));
+            check(module, names)
+                .err()
+                .map(|e| format!("[analyzers.{}] {}", module, e))
+        })
+        .collect();
+    errors.extend(selections.iter().filter_map(|(module, selection)| {
+        let percent = selection.rollout_percent?;
+        (percent > rollout::MAX_PERCENT).then(|| {
+            format!(
+                "[analyzers.{}] rollout_percent = {} is invalid. Must be 0-{}",
+                module,
+                percent,
+                rollout::MAX_PERCENT
+            )
+        })
+    }));
+    errors
+}
+
+/// Whether an analyzer of `module` with sub-rules `rules` can report
+/// anything under `config`'s selection
+pub(crate) fn selects_any(module: Option<&str>, rules: &[&str], config: &RevetConfig) -> bool {
+    match module {
+        Some(module) if !rules.is_empty() => {
+            let selection = config.rule_selection(module);
+            rules.iter().any(|rule| selection.allows(rule))
+        }
+        _ => true,
+    }
+}
+
+/// Qualify each finding's sub-rule with `module` and drop the ones the
+/// config deselects
+pub(crate) fn apply(
+    module: Option<&str>,
+    findings: Vec<Finding>,
+    config: &RevetConfig,
+) -> Vec<Finding> {
+    let Some(module) = module else {
+        return findings;
+    };
+    let selection: &RuleSelection = config.rule_selection(module);
+    findings
+        .into_iter()
+        .filter_map(|mut finding| {
+            if let Some(rule) = finding.rule.take() {
+                if !selection.allows(&rule) {
+                    trace::record(&finding.file, finding.line, Decision::Filtered, || {
+                        format!(
+                            "sub-rule `{}` is not selected in [analyzers.{}]",
+                            rule, module
+                        )
+                    });
+                    return None;
+                }
+                finding.rule = Some(format!("{}:{}", module, rule));
+            }
+            Some(finding)
+        })
+        .collect()
+}
@@ -1,124 +1,54 @@
-//! Sensitive logging analyzer — detects secrets/credentials passed to log or print calls
+//! Sensitive logging analyzer — detects secrets and PII passed to log or print calls
 //!
-//! Logging sensitive data such as passwords, tokens, or API keys exposes secrets in
-//! log files, which are often stored or forwarded to third-party aggregators.
-//! This analyzer flags log/print calls where a sensitive-named variable appears as
-//! an argument (CWE-532: Insertion of Sensitive Information into Log File).
+//! Logging sensitive data such as passwords, tokens, or customer emails exposes
+//! it in log files, which are often stored or forwarded to third-party
+//! aggregators (CWE-532: Insertion of Sensitive Information into Log File).
 //!
-//! Covers Python, JavaScript/TypeScript, PHP, Go, Java, and Ruby.
+//! Python, JavaScript/TypeScript, Go, Java and Rust log calls are inspected
+//! argument by argument (see [`arguments`]): sensitive-named values, whole
+//! request/response/user objects, and exceptions logged with a raw body are
+//! flagged, with the offending argument's columns on the finding. PHP and Ruby
+//! fall back to a line scan for sensitive `$variables` / identifiers passed to
+//! `error_log` / `var_dump` / `puts` / `p`.
+//!
+//! Name heuristics misfire, so every finding has Medium confidence. The name
+//! list is configurable with `[sensitive_logging]`.
+
+pub mod arguments;
 
 use crate::analyzer::{make_finding, Analyzer};
 use crate::config::RevetConfig;
-use crate::finding::{Finding, FixKind, Severity};
+use crate::finding::{Confidence, Finding, FixKind, Severity};
+use arguments::{Leak, LeakKind, SensitiveNames};
 use regex::Regex;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
-struct LogPattern {
-    name: &'static str,
-    regex: Regex,
-    severity: Severity,
-    suggestion: &'static str,
+/// A line-scanned log call (PHP and Ruby)
+struct LinePattern {
+    label: &'static str,
+    /// Matches the call up to its first argument
+    call: Regex,
+    /// Matches candidate arguments in the rest of the line
+    argument: Regex,
     extensions: &'static [&'static str],
 }
 
-/// Sensitive identifier names (word-boundary matched in each regex)
-///
-/// Covers common naming conventions across languages. PHP patterns additionally
-/// require the `$` sigil. Go/Java patterns include camelCase variants.
-const SENSITIVE: &str =
-    r"password|passwd|pwd|secret|token|api[_-]?key|credential|auth[_-]?key|private[_-]?key";
-
-const SENSITIVE_CAMEL: &str = r"password|passwd|pwd|secret|token|apiKey|api[_-]?key|credential|authKey|auth[_-]?key|privateKey|private[_-]?key";
-
-fn patterns() -> &'static [LogPattern] {
-    static PATTERNS: OnceLock<Vec<LogPattern>> = OnceLock::new();
+fn line_patterns() -> &'static [LinePattern] {
+    static PATTERNS: OnceLock<Vec<LinePattern>> = OnceLock::new();
     PATTERNS.get_or_init(|| {
         vec![
-            // ── Python ────────────────────────────────────────────────────────
-            LogPattern {
-                name: "sensitive variable passed to Python logging call",
-                regex: Regex::new(&format!(
-                    r"(?i)\b(?:logging|log|logger)\.(?:debug|info|warning|warn|error|critical|exception)\s*\(.*\b(?:{SENSITIVE})\b"
-                ))
-                .unwrap(),
-                severity: Severity::Warning,
-                suggestion: "Never log raw credentials; redact or omit the sensitive field before logging",
-                extensions: &["py"],
-            },
-            LogPattern {
-                name: "sensitive variable passed to Python print()",
-                regex: Regex::new(&format!(
-                    r"(?i)\bprint\s*\(.*\b(?:{SENSITIVE})\b"
-                ))
-                .unwrap(),
-                severity: Severity::Warning,
-                suggestion: "Remove the sensitive value from the print() call",
-                extensions: &["py"],
-            },
-            // ── JavaScript / TypeScript ───────────────────────────────────────
-            LogPattern {
-                name: "sensitive variable passed to console.*",
-                regex: Regex::new(&format!(
-                    r"(?i)\bconsole\.(?:log|info|warn|error|debug|trace)\s*\(.*\b(?:{SENSITIVE})\b"
-                ))
-                .unwrap(),
-                severity: Severity::Warning,
-                suggestion: "Remove sensitive values from console calls; use a redaction helper",
-                extensions: &["js", "ts", "jsx", "tsx", "mjs", "cjs"],
-            },
-            LogPattern {
-                name: "sensitive variable passed to logger.* (JS/TS)",
-                regex: Regex::new(&format!(
-                    r"(?i)\blogger\.(?:log|info|warn|error|debug|trace|fatal)\s*\(.*\b(?:{SENSITIVE})\b"
-                ))
-                .unwrap(),
-                severity: Severity::Warning,
-                suggestion: "Redact or omit the sensitive field before passing it to the logger",
-                extensions: &["js", "ts", "jsx", "tsx", "mjs", "cjs"],
-            },
-            // ── Go ────────────────────────────────────────────────────────────
-            LogPattern {
-                name: "sensitive variable passed to fmt.Print / log.Print (Go)",
-                regex: Regex::new(&format!(
-                    r"\b(?:fmt\.Print(?:f|ln)?|log\.Print(?:f|ln)?|log\.Fatal(?:f|ln)?|log\.Panic(?:f|ln)?)\s*\(.*\b(?:{SENSITIVE_CAMEL})\b"
-                ))
-                .unwrap(),
-                severity: Severity::Warning,
-                suggestion: "Redact the sensitive field before logging; use structured logging with redaction",
-                extensions: &["go"],
-            },
-            // ── Java ──────────────────────────────────────────────────────────
-            LogPattern {
-                name: "sensitive variable passed to System.out.println or logger (Java)",
-                regex: Regex::new(&format!(
-                    r"(?i)(?:System\.out\.print(?:ln)?|(?:log|logger)\.(?:info|debug|warn|error|trace|fatal))\s*\(.*\b(?:{SENSITIVE_CAMEL})\b"
-                ))
-                .unwrap(),
-                severity: Severity::Warning,
-                suggestion: "Do not log raw credentials; mask or omit the sensitive field",
-                extensions: &["java"],
-            },
-            // ── PHP ───────────────────────────────────────────────────────────
-            LogPattern {
-                name: "sensitive variable passed to error_log / var_dump (PHP)",
-                regex: Regex::new(
-                    r"(?i)\b(?:error_log|var_dump|print_r)\s*\(.*\$(?:password|passwd|secret|token|api_key|credential|auth_key|private_key)\b",
-                )
-                .unwrap(),
-                severity: Severity::Warning,
-                suggestion: "Never dump raw credentials to error_log or var_dump",
+            LinePattern {
+                label: "error_log / var_dump (PHP)",
+                call: Regex::new(r"(?i)\b(?:error_log|var_dump|print_r)\s*\(").unwrap(),
+                argument: Regex::new(r"\$[A-Za-z_]\w*(?:->[A-Za-z_]\w*)*").unwrap(),
                 extensions: &["php"],
             },
-            // ── Ruby ──────────────────────────────────────────────────────────
-            LogPattern {
-                name: "sensitive variable passed to puts / p (Ruby)",
-                regex: Regex::new(&format!(
-                    r"(?i)\b(?:puts|p|pp|print)\s+(?:\w+\.)?\b(?:{SENSITIVE})\b"
-                ))
-                .unwrap(),
-                severity: Severity::Warning,
-                suggestion: "Remove the sensitive variable from the puts/p call",
+            LinePattern {
+                label: "puts / p (Ruby)",
+                call: Regex::new(r"\b(?:puts|p|pp|print)\s+").unwrap(),
+                argument: Regex::new(r"^@?[A-Za-z_]\w*(?:\.[A-Za-z_]\w*)*").unwrap(),
                 extensions: &["rb"],
             },
         ]
@@ -132,12 +62,21 @@ const BINARY_EXTENSIONS: &[&str] = &[
     "avi", "mov", "wav", "flac", "sqlite", "db",
 ];
 
-/// Analyzer that detects sensitive data (credentials, secrets) passed to log/print calls
-pub struct SensitiveLoggingAnalyzer;
+/// Analyzer that detects sensitive data (credentials, PII) passed to log/print calls
+pub struct SensitiveLoggingAnalyzer {
+    names: SensitiveNames,
+}
 
 impl SensitiveLoggingAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self::with_names(&[], &[])
+    }
+
+    /// Built-in sensitive names plus `sensitive_names`, minus `allow_names`
+    pub fn with_names(sensitive_names: &[String], allow_names: &[String]) -> Self {
+        Self {
+            names: SensitiveNames::new(sensitive_names, allow_names),
+        }
     }
 
     fn should_scan(path: &Path) -> bool {
@@ -148,42 +87,127 @@ impl SensitiveLoggingAnalyzer {
         !BINARY_EXTENSIONS.contains(&ext.as_str())
     }
 
-    fn scan_file(path: &Path) -> Vec<Finding> {
+    fn scan_file(&self, path: &Path) -> Vec<Finding> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => self.scan_source(path, &content),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn scan_source(&self, path: &Path, content: &str) -> Vec<Finding> {
+        let leaks = if arguments::supports(path) {
+            arguments::scan(path, content, &self.names)
+        } else {
+            self.scan_lines(path, content)
+        };
+
+        let mut seen_lines = HashSet::new();
+        let mut findings = Vec::new();
+        for leak in leaks {
+            let (line, column) = position(content, leak.start);
+            if !seen_lines.insert(line) {
+                continue; // One finding per line
+            }
+            let end_column = match position(content, leak.end) {
+                (end_line, end_column) if end_line == line => Some(end_column),
+                _ => None,
+            };
+            let (message, suggestion) = describe(&leak);
+            let mut finding = make_finding(
+                Severity::Warning,
+                message,
+                path.to_path_buf(),
+                line,
+                Some(suggestion.to_string()),
+                Some(FixKind::Suggestion),
+            );
+            finding.rule = Some("sensitive_logging".to_string());
+            finding.column = Some(column);
+            finding.end_column = end_column;
+            finding.confidence = Confidence::Medium;
+            findings.push(finding);
+        }
+        findings
+    }
+
+    /// PHP / Ruby: the first sensitive argument after a log call on each line
+    fn scan_lines(&self, path: &Path, content: &str) -> Vec<Leak> {
         let ext = path
             .extension()
             .and_then(|e| e.to_str())
             .unwrap_or("")
             .to_lowercase();
 
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return Vec::new(),
-        };
-
-        let all_patterns = patterns();
-        let mut findings = Vec::new();
-
-        for (line_num, line) in content.lines().enumerate() {
-            for pat in all_patterns {
-                if !pat.extensions.is_empty() && !pat.extensions.contains(&ext.as_str()) {
+        let mut leaks = Vec::new();
+        let mut offset = 0;
+        for line in content.split_inclusive('\n') {
+            for pat in line_patterns() {
+                if !pat.extensions.contains(&ext.as_str()) {
                     continue;
                 }
-                if !pat.regex.is_match(line) {
+                let Some(call) = pat.call.find(line) else {
                     continue;
+                };
+                let rest = &line[call.end()..];
+                let hit = pat.argument.find_iter(rest).find(|arg| {
+                    arg.as_str()
+                        .split(['.', '>'])
+                        .map(|s| s.trim_end_matches('-'))
+                        .any(|segment| self.names.matches(segment))
+                });
+                if let Some(arg) = hit {
+                    let start = offset + call.end() + arg.start();
+                    leaks.push(Leak {
+                        kind: LeakKind::Sensitive,
+                        start,
+                        end: start + arg.len(),
+                        text: arg.as_str().to_string(),
+                        label: pat.label,
+                    });
+                    break;
                 }
-                findings.push(make_finding(
-                    pat.severity,
-                    format!("Sensitive data in log: {}", pat.name),
-                    path.to_path_buf(),
-                    line_num + 1,
-                    Some(pat.suggestion.to_string()),
-                    Some(FixKind::Suggestion),
-                ));
-                break; // One finding per line
             }
+            offset += line.len();
         }
+        leaks
+    }
+}
 
-        findings
+/// 1-based line and character column of a byte offset
+fn position(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+fn describe(leak: &Leak) -> (String, &'static str) {
+    let text = if leak.text.chars().count() > 60 {
+        format!("{}...", leak.text.chars().take(57).collect::<String>())
+    } else {
+        leak.text.clone()
+    };
+    match leak.kind {
+        LeakKind::Sensitive => (
+            format!("Sensitive data in log: `{}` passed to {}", text, leak.label),
+            "Log a redacted or structured field instead (e.g. a masked value or an ID)",
+        ),
+        LeakKind::Object => (
+            format!(
+                "Sensitive data in log: entire `{}` object passed to {}",
+                text, leak.label
+            ),
+            "Log only the fields you need (e.g. `user.id`, `request.method`) instead of the whole object",
+        ),
+        LeakKind::ExceptionBody => (
+            format!(
+                "Sensitive data in log: exception logged with raw body `{}` in {}",
+                text, leak.label
+            ),
+            "Log the exception with a request or correlation ID instead of the raw body",
+        ),
     }
 }
 
@@ -206,14 +230,37 @@ impl Analyzer for SensitiveLoggingAnalyzer {
         config.modules.security
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("security")
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        &["sensitive_logging"]
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Self::should_scan(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
     fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
         let mut findings = Vec::new();
         for file in files {
             if !Self::should_scan(file) {
                 continue;
             }
-            findings.extend(Self::scan_file(file));
+            findings.extend(self.scan_file(file));
         }
         findings
     }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        if !Self::should_scan(path) {
+            return Vec::new();
+        }
+        self.scan_source(path, content)
+    }
 }
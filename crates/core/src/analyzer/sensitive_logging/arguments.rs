@@ -0,0 +1,876 @@
+//! Log call argument inspection
+//!
+//! Python, JavaScript/TypeScript, Go and Java sources are parsed with
+//! tree-sitter and every call matching the per-language log table has its
+//! arguments walked; Rust log macros get the same treatment over their token
+//! trees. Three shapes are reported:
+//!
+//! - an identifier, member or string key whose name matches the sensitive-name
+//!   list (`f"sent to {email}"`, `"pwd: " + user.password`,
+//!   `headers["Authorization"]`)
+//! - a whole request/response/user object (`logger.info(req)`)
+//! - an exception logged together with a raw body (`log.error(e, req.body)`)
+//!
+//! Values passed through a sanitizer (`mask(email)`, `len(token)`) or reduced
+//! to a derived member (`token.length`, `user.id`) are left alone.
+
+use crate::analyzer::magic_numbers::tunables::name_words;
+use crate::parser::grammar::{language_for, Family, Grammar};
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Built-in sensitive names, matched against identifier words
+pub const DEFAULT_SENSITIVE_NAMES: &[&str] = &[
+    "password",
+    "passwd",
+    "pwd",
+    "secret",
+    "token",
+    "api_key",
+    "apikey",
+    "credential",
+    "credentials",
+    "auth_key",
+    "private_key",
+    "email",
+    "ssn",
+    "authorization",
+    "card",
+    "cvv",
+];
+
+/// A trailing word that turns a sensitive name into a harmless derived value
+/// (`token_count`, `password_length`, `card_id`)
+const SAFE_SUFFIXES: &[&str] = &[
+    "count", "length", "len", "size", "type", "kind", "ttl", "expiry", "expires", "hash", "hashed",
+    "masked", "redacted", "valid", "present", "enabled", "set", "id", "ids",
+];
+
+/// A leading word that marks a boolean (`has_token`, `isEmailVerified`)
+const PREDICATE_PREFIXES: &[&str] = &["is", "has", "should"];
+
+/// Words that mark a value as already sanitized (`masked_email`, `redactedToken`)
+const SANITIZED_WORDS: &[&str] = &[
+    "masked",
+    "redacted",
+    "hashed",
+    "sanitized",
+    "obfuscated",
+    "encrypted",
+    "anonymized",
+];
+
+/// Call names whose result is safe to log whatever their argument
+const SANITIZERS: &[&str] = &[
+    "mask",
+    "redact",
+    "hash",
+    "digest",
+    "sanitize",
+    "scrub",
+    "obfuscate",
+    "anonymize",
+    "encrypt",
+    "fingerprint",
+    "len",
+    "length",
+    "count",
+    "size",
+    "isinstance",
+    "type",
+    "bool",
+];
+
+/// Identifier words naming a whole request, response or user object
+const OBJECT_WORDS: &[&str] = &[
+    "req", "request", "res", "resp", "response", "user", "ctx", "context",
+];
+
+/// Conventional names for a caught exception or error value
+const EXCEPTION_NAMES: &[&str] = &["e", "ex", "exc", "err", "error", "exception", "throwable"];
+
+/// Members holding an unparsed request or response body
+const BODY_MEMBERS: &[&str] = &[
+    "body",
+    "raw_body",
+    "rawbody",
+    "payload",
+    "request_body",
+    "requestbody",
+];
+
+/// Members and methods that expose the body of a request/response object
+/// (`request.data`, `await res.text()`)
+const BODY_ACCESSORS: &[&str] = &["data", "text", "content", "json", "get_data", "get_json"];
+
+/// Getter calls whose string argument names the value returned
+/// (`req.headers.get("Authorization")`, `os.Getenv("API_TOKEN")`)
+const KEYED_GETTERS: &[&str] = &["get", "header", "getheader", "getenv", "env"];
+
+/// The sensitive-name list after config additions and removals
+#[derive(Debug, Clone)]
+pub struct SensitiveNames {
+    names: Vec<Vec<String>>,
+    allowed: Vec<Vec<String>>,
+}
+
+impl SensitiveNames {
+    /// Built-in names plus `extra`, minus anything in `allow`
+    ///
+    /// An `allow` entry both removes a list entry and exempts identifiers
+    /// spelled exactly like it (`allow_names = ["csrf_token"]`).
+    pub fn new(extra: &[String], allow: &[String]) -> Self {
+        let allowed: Vec<Vec<String>> = allow
+            .iter()
+            .map(|n| name_words(n))
+            .filter(|w| !w.is_empty())
+            .collect();
+        let mut names: Vec<Vec<String>> = Vec::new();
+        for name in DEFAULT_SENSITIVE_NAMES
+            .iter()
+            .map(|n| n.to_string())
+            .chain(extra.iter().cloned())
+        {
+            let words = name_words(&name);
+            if !words.is_empty() && !allowed.contains(&words) && !names.contains(&words) {
+                names.push(words);
+            }
+        }
+        Self { names, allowed }
+    }
+
+    /// Whether an identifier names a sensitive value
+    pub fn matches(&self, name: &str) -> bool {
+        let words = words(name);
+        if words.is_empty() || self.allowed.contains(&words) || is_derived(&words) {
+            return false;
+        }
+        self.names
+            .iter()
+            .any(|seq| words.windows(seq.len()).any(|w| w == seq.as_slice()))
+    }
+
+    /// Whether an identifier names a whole request/response/user object
+    fn is_object(&self, name: &str) -> bool {
+        let words = words(name);
+        !self.allowed.contains(&words)
+            && words
+                .last()
+                .is_some_and(|w| OBJECT_WORDS.contains(&w.as_str()))
+    }
+}
+
+impl Default for SensitiveNames {
+    fn default() -> Self {
+        Self::new(&[], &[])
+    }
+}
+
+fn words(name: &str) -> Vec<String> {
+    name_words(name.trim_start_matches(['$', '@']))
+}
+
+/// `token_count`, `has_token`, `maskedEmail`
+fn is_derived(words: &[String]) -> bool {
+    if words.len() > 1
+        && (SAFE_SUFFIXES.contains(&words[words.len() - 1].as_str())
+            || PREDICATE_PREFIXES.contains(&words[0].as_str()))
+    {
+        return true;
+    }
+    words.iter().any(|w| SANITIZED_WORDS.contains(&w.as_str()))
+}
+
+/// A member that reduces its object to a harmless value (`token.length`,
+/// `user.id`) — a single safe word is enough here
+fn is_derived_member(name: &str) -> bool {
+    let words = words(name);
+    words
+        .last()
+        .is_some_and(|w| SAFE_SUFFIXES.contains(&w.as_str()))
+        || is_derived(&words)
+}
+
+fn is_sanitizer(name: &str) -> bool {
+    let words = words(name);
+    words
+        .first()
+        .is_some_and(|w| PREDICATE_PREFIXES.contains(&w.as_str()))
+        || words.iter().any(|w| SANITIZERS.contains(&w.as_str()))
+}
+
+fn is_body_name(name: &str) -> bool {
+    words(name)
+        .last()
+        .is_some_and(|w| w == "body" || w == "payload")
+}
+
+fn normalized(name: &str) -> String {
+    name.trim_start_matches('_').to_lowercase()
+}
+
+// ── Log call table ───────────────────────────────────────────────────────────
+
+/// One row of the log call table
+///
+/// `receivers` are compared with the full receiver text (`System.out`) and
+/// with its last segment, lowercased and stripped of leading underscores
+/// (`self._logger` → `logger`); `""` matches a bare call.
+struct LogCall {
+    family: Family,
+    receivers: &'static [&'static str],
+    methods: &'static [&'static str],
+    label: &'static str,
+}
+
+const GO_LOGGER_METHODS: &[&str] = &[
+    "Debug", "Debugf", "Debugw", "Info", "Infof", "Infow", "Warn", "Warnf", "Warnw", "Error",
+    "Errorf", "Errorw", "Fatal", "Fatalf", "Fatalw",
+];
+
+const LOG_CALLS: &[LogCall] = &[
+    LogCall {
+        family: Family::Python,
+        receivers: &["logging", "log", "logger"],
+        methods: &[
+            "debug",
+            "info",
+            "warning",
+            "warn",
+            "error",
+            "critical",
+            "exception",
+            "fatal",
+            "log",
+        ],
+        label: "Python logging call",
+    },
+    LogCall {
+        family: Family::Python,
+        receivers: &[""],
+        methods: &["print"],
+        label: "print()",
+    },
+    LogCall {
+        family: Family::Js,
+        receivers: &["console"],
+        methods: &["log", "info", "warn", "error", "debug", "trace"],
+        label: "console.*",
+    },
+    LogCall {
+        family: Family::Js,
+        receivers: &["logger", "log"],
+        methods: &[
+            "log", "info", "warn", "error", "debug", "trace", "fatal", "verbose",
+        ],
+        label: "logger.*",
+    },
+    LogCall {
+        family: Family::Go,
+        receivers: &["fmt"],
+        methods: &[
+            "Print", "Printf", "Println", "Fprint", "Fprintf", "Fprintln",
+        ],
+        label: "fmt.Print / log.Print",
+    },
+    LogCall {
+        family: Family::Go,
+        receivers: &["log"],
+        methods: &[
+            "Print", "Printf", "Println", "Fatal", "Fatalf", "Fatalln", "Panic", "Panicf",
+            "Panicln",
+        ],
+        label: "fmt.Print / log.Print",
+    },
+    LogCall {
+        family: Family::Go,
+        receivers: &["log", "logger", "slog", "logrus", "zap", "sugar"],
+        methods: GO_LOGGER_METHODS,
+        label: "Go logger call",
+    },
+    LogCall {
+        family: Family::Java,
+        receivers: &["System.out", "System.err"],
+        methods: &["print", "println", "printf"],
+        label: "System.out.println",
+    },
+    LogCall {
+        family: Family::Java,
+        receivers: &["log", "logger"],
+        methods: &["trace", "debug", "info", "warn", "error", "fatal"],
+        label: "SLF4J logger call",
+    },
+    LogCall {
+        family: Family::Rust,
+        receivers: &["", "tracing", "log"],
+        methods: &["trace", "debug", "info", "warn", "error", "event"],
+        label: "tracing/log macro",
+    },
+    LogCall {
+        family: Family::Rust,
+        receivers: &[""],
+        methods: &["println", "eprintln", "print", "eprint", "dbg"],
+        label: "println!",
+    },
+];
+
+// ── Results ──────────────────────────────────────────────────────────────────
+
+/// What a log call leaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeakKind {
+    /// A sensitive-named value
+    Sensitive,
+    /// A whole request/response/user object
+    Object,
+    /// An exception together with a raw body
+    ExceptionBody,
+}
+
+/// One offending log call argument
+#[derive(Debug, Clone)]
+pub struct Leak {
+    pub kind: LeakKind,
+    /// Byte range of the offending argument
+    pub start: usize,
+    pub end: usize,
+    /// Source text of the offending argument
+    pub text: String,
+    /// Log call label from the pattern table (`console.*`)
+    pub label: &'static str,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    start: usize,
+    end: usize,
+}
+
+impl From<Node<'_>> for Span {
+    fn from(node: Node) -> Self {
+        Self {
+            start: node.start_byte(),
+            end: node.end_byte(),
+        }
+    }
+}
+
+/// What the arguments of one log call contain; the first hit of each kind wins
+#[derive(Default)]
+struct Args {
+    sensitive: Option<Span>,
+    object: Option<Span>,
+    body: Option<Span>,
+    exception: bool,
+}
+
+impl Args {
+    fn sensitive(&mut self, span: impl Into<Span>) {
+        self.sensitive.get_or_insert(span.into());
+    }
+
+    fn object(&mut self, span: impl Into<Span>) {
+        self.object.get_or_insert(span.into());
+    }
+
+    fn body(&mut self, span: impl Into<Span>) {
+        self.body.get_or_insert(span.into());
+    }
+
+    /// Most severe finding: sensitive value, then exception plus body, then object
+    fn leak(self) -> Option<(LeakKind, Span)> {
+        if let Some(span) = self.sensitive {
+            return Some((LeakKind::Sensitive, span));
+        }
+        if self.exception {
+            if let Some(span) = self.body {
+                return Some((LeakKind::ExceptionBody, span));
+            }
+        }
+        self.object.map(|span| (LeakKind::Object, span))
+    }
+}
+
+// ── Scanner ──────────────────────────────────────────────────────────────────
+
+/// Whether `path` is covered by the AST pass
+pub fn supports(path: &Path) -> bool {
+    grammar(path).is_some()
+}
+
+fn grammar(path: &Path) -> Option<Grammar> {
+    language_for(path).filter(|g| {
+        matches!(
+            g.family,
+            Family::Python | Family::Js | Family::Go | Family::Java | Family::Rust
+        )
+    })
+}
+
+/// Inspect every log call in one source, in source order
+pub fn scan(path: &Path, content: &str, names: &SensitiveNames) -> Vec<Leak> {
+    let Some(grammar) = grammar(path) else {
+        return Vec::new();
+    };
+    let Some(tree) = grammar.parse(content) else {
+        return Vec::new();
+    };
+
+    let mut scanner = Scanner {
+        family: grammar.family,
+        source: content.as_bytes(),
+        names,
+        leaks: Vec::new(),
+    };
+    scanner.walk(tree.root_node());
+    scanner.leaks.sort_by_key(|l| l.start);
+    scanner.leaks
+}
+
+/// How the argument walk sees a node
+enum Shape<'t> {
+    Ident,
+    Member {
+        object: Node<'t>,
+        property: Node<'t>,
+    },
+    Call {
+        receiver: Option<Node<'t>>,
+        method: Option<Node<'t>>,
+        args: Option<Node<'t>>,
+    },
+    Subscript {
+        object: Node<'t>,
+        index: Node<'t>,
+    },
+    Keyword {
+        name: Option<Node<'t>>,
+        value: Node<'t>,
+    },
+    /// `typeof token`
+    TypeOf,
+    Other,
+}
+
+struct Scanner<'a> {
+    family: Family,
+    source: &'a [u8],
+    names: &'a SensitiveNames,
+    leaks: Vec<Leak>,
+}
+
+impl Scanner<'_> {
+    fn text(&self, node: Node) -> &str {
+        node.utf8_text(self.source).unwrap_or("")
+    }
+
+    fn walk(&mut self, node: Node) {
+        if self.family == Family::Rust {
+            if node.kind() == "macro_invocation" {
+                self.rust_macro(node);
+            }
+        } else if let Shape::Call {
+            receiver,
+            method: Some(method),
+            args: Some(args),
+        } = self.shape(node)
+        {
+            self.call(receiver, method, args);
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.walk(child);
+        }
+    }
+
+    fn shape<'t>(&self, node: Node<'t>) -> Shape<'t> {
+        let field = |name: &str| node.child_by_field_name(name);
+        let member = |object: Option<Node<'t>>, property: Option<Node<'t>>| match (object, property)
+        {
+            (Some(object), Some(property)) => Shape::Member { object, property },
+            _ => Shape::Other,
+        };
+        let call = |callee: Option<Node<'t>>, args: Option<Node<'t>>| {
+            let (receiver, method) = match callee.map(|c| (c, self.shape(c))) {
+                Some((_, Shape::Member { object, property })) => (Some(object), Some(property)),
+                Some((c, Shape::Ident)) => (None, Some(c)),
+                _ => (None, None),
+            };
+            Shape::Call {
+                receiver,
+                method,
+                args,
+            }
+        };
+        match (self.family, node.kind()) {
+            (_, "identifier") | (Family::Js, "shorthand_property_identifier") => Shape::Ident,
+            (Family::Python, "attribute") => member(field("object"), field("attribute")),
+            (Family::Js, "member_expression") => member(field("object"), field("property")),
+            (Family::Go, "selector_expression") => member(field("operand"), field("field")),
+            (Family::Java, "field_access") => member(field("object"), field("field")),
+            (Family::Python, "call") => call(field("function"), field("arguments")),
+            (Family::Js | Family::Go, "call_expression") => {
+                call(field("function"), field("arguments"))
+            }
+            (Family::Java, "method_invocation") => Shape::Call {
+                receiver: field("object"),
+                method: field("name"),
+                args: field("arguments"),
+            },
+            (Family::Python, "subscript") => match (field("value"), field("subscript")) {
+                (Some(object), Some(index)) => Shape::Subscript { object, index },
+                _ => Shape::Other,
+            },
+            (Family::Js, "subscript_expression") | (Family::Go, "index_expression") => {
+                let object = field("object").or_else(|| field("operand"));
+                match (object, field("index")) {
+                    (Some(object), Some(index)) => Shape::Subscript { object, index },
+                    _ => Shape::Other,
+                }
+            }
+            (Family::Java, "array_access") => match (field("array"), field("index")) {
+                (Some(object), Some(index)) => Shape::Subscript { object, index },
+                _ => Shape::Other,
+            },
+            (Family::Python, "keyword_argument") | (Family::Python | Family::Js, "pair") => {
+                match field("value") {
+                    Some(value) => Shape::Keyword {
+                        name: field("name").or_else(|| field("key")),
+                        value,
+                    },
+                    None => Shape::Other,
+                }
+            }
+            (Family::Js, "unary_expression")
+                if node.child(0).is_some_and(|op| op.kind() == "typeof") =>
+            {
+                Shape::TypeOf
+            }
+            _ => Shape::Other,
+        }
+    }
+
+    /// Last name segment of a receiver (`self._logger` → `_logger`)
+    fn last_segment<'s>(&'s self, node: Node) -> &'s str {
+        match self.shape(node) {
+            Shape::Member { property, .. } => self.text(property),
+            Shape::Ident => self.text(node),
+            _ => "",
+        }
+    }
+
+    fn log_call(&self, receiver: Option<Node>, method: &str) -> Option<&'static LogCall> {
+        let (full, last) = match receiver {
+            Some(r) => (self.text(r), normalized(self.last_segment(r))),
+            None => ("", String::new()),
+        };
+        LOG_CALLS.iter().find(|entry| {
+            entry.family == self.family
+                && entry.methods.contains(&method)
+                && entry.receivers.iter().any(|r| match receiver {
+                    None => r.is_empty(),
+                    Some(_) => !r.is_empty() && (*r == full || *r == last),
+                })
+        })
+    }
+
+    fn call(&mut self, receiver: Option<Node>, method: Node, args: Node) {
+        let method = self.text(method);
+        let Some(entry) = self.log_call(receiver, method) else {
+            return;
+        };
+        let mut acc = Args {
+            exception: method == "exception",
+            ..Default::default()
+        };
+        let mut cursor = args.walk();
+        for arg in args.named_children(&mut cursor) {
+            self.inspect(arg, false, &mut acc);
+        }
+        if let Some((kind, span)) = acc.leak() {
+            self.push(kind, span, entry.label);
+        }
+    }
+
+    fn push(&mut self, kind: LeakKind, span: Span, label: &'static str) {
+        let text = std::str::from_utf8(&self.source[span.start..span.end]).unwrap_or("");
+        self.leaks.push(Leak {
+            kind,
+            start: span.start,
+            end: span.end,
+            text: text.split_whitespace().collect::<Vec<_>>().join(" "),
+            label,
+        });
+    }
+
+    /// Walk one argument expression; `receiver` is set while walking the
+    /// object of a member access or method call
+    fn inspect(&self, node: Node, receiver: bool, acc: &mut Args) {
+        match self.shape(node) {
+            Shape::Ident => {
+                let name = self.text(node);
+                if self.names.matches(name) {
+                    acc.sensitive(node);
+                } else if !receiver {
+                    if EXCEPTION_NAMES.contains(&name) {
+                        acc.exception = true;
+                    } else if self.names.is_object(name) {
+                        acc.object(node);
+                    } else if is_body_name(name) {
+                        acc.body(node);
+                    }
+                }
+            }
+            Shape::Member { object, property } => {
+                let name = self.text(property);
+                let lower = normalized(name);
+                if BODY_MEMBERS.contains(&lower.as_str())
+                    || (BODY_ACCESSORS.contains(&lower.as_str())
+                        && self.names.is_object(self.last_segment(object)))
+                {
+                    acc.body(node);
+                    return;
+                }
+                if self.names.matches(name) {
+                    acc.sensitive(node);
+                } else if !is_derived_member(name) {
+                    self.inspect(object, true, acc);
+                }
+            }
+            Shape::Call {
+                receiver: object,
+                method,
+                args,
+            } => {
+                let name = method.map(|m| self.text(m)).unwrap_or("");
+                if is_sanitizer(name) {
+                    return;
+                }
+                if let Some(object) = object {
+                    if BODY_ACCESSORS.contains(&normalized(name).as_str())
+                        && self.names.is_object(self.last_segment(object))
+                    {
+                        acc.body(node);
+                        return;
+                    }
+                }
+                if self.names.matches(name) {
+                    acc.sensitive(node);
+                    return;
+                }
+                if let Some(object) = object {
+                    self.inspect(object, true, acc);
+                }
+                let Some(args) = args else {
+                    return;
+                };
+                let keyed = KEYED_GETTERS.contains(&normalized(name).replace('_', "").as_str());
+                let mut cursor = args.walk();
+                for arg in args.named_children(&mut cursor) {
+                    if keyed && self.string_key(arg).is_some_and(|k| self.names.matches(k)) {
+                        acc.sensitive(node);
+                    }
+                    self.inspect(arg, false, acc);
+                }
+            }
+            Shape::Subscript { object, index } => match self.string_key(index) {
+                Some(key) if self.names.matches(key) => acc.sensitive(node),
+                Some(key) if is_derived(&words(key)) => {}
+                _ => self.inspect(object, true, acc),
+            },
+            Shape::Keyword { name, value } => {
+                if name.is_some_and(|n| self.text(n) == "exc_info") && self.text(value) != "False" {
+                    acc.exception = true;
+                }
+                self.inspect(value, false, acc);
+            }
+            Shape::TypeOf => {}
+            Shape::Other => {
+                let mut cursor = node.walk();
+                for child in node.named_children(&mut cursor) {
+                    self.inspect(child, false, acc);
+                }
+            }
+        }
+    }
+
+    /// Contents of a plain string literal used as a key
+    fn string_key<'s>(&'s self, node: Node) -> Option<&'s str> {
+        let kind = node.kind();
+        if !(kind == "string" || kind.ends_with("string_literal")) {
+            return None;
+        }
+        let mut cursor = node.walk();
+        if node
+            .named_children(&mut cursor)
+            .any(|c| c.kind() == "interpolation")
+        {
+            return None;
+        }
+        Some(
+            self.text(node)
+                .trim_matches(|c| matches!(c, '"' | '\'' | '`')),
+        )
+    }
+
+    // ── Rust macros ──────────────────────────────────────────────────────────
+
+    fn rust_macro(&mut self, node: Node) {
+        let Some(callee) = node.child_by_field_name("macro") else {
+            return;
+        };
+        let (path, name) = match callee.kind() {
+            "scoped_identifier" => (
+                callee.child_by_field_name("path").map(|p| self.text(p)),
+                callee.child_by_field_name("name").map(|n| self.text(n)),
+            ),
+            _ => (None, Some(self.text(callee))),
+        };
+        let Some(name) = name else {
+            return;
+        };
+        let Some(entry) = LOG_CALLS.iter().find(|e| {
+            e.family == Family::Rust
+                && e.methods.contains(&name)
+                && e.receivers.contains(&path.unwrap_or(""))
+        }) else {
+            return;
+        };
+        let mut cursor = node.walk();
+        let Some(tokens) = node
+            .children(&mut cursor)
+            .find(|c| c.kind() == "token_tree")
+        else {
+            return;
+        };
+        let mut acc = Args::default();
+        self.rust_tokens(tokens, &mut acc);
+        if let Some((kind, span)) = acc.leak() {
+            self.push(kind, span, entry.label);
+        }
+    }
+
+    /// Walk a macro token tree, treating `a.b.c(..)` runs as one expression
+    fn rust_tokens(&self, tree: Node, acc: &mut Args) {
+        let mut cursor = tree.walk();
+        let tokens: Vec<Node> = tree.children(&mut cursor).collect();
+        let kind = |i: usize| tokens.get(i).map(|t| t.kind()).unwrap_or("");
+        let mut i = 0;
+        while i < tokens.len() {
+            match kind(i) {
+                "token_tree" => {
+                    self.rust_tokens(tokens[i], acc);
+                    i += 1;
+                }
+                "string_literal" => {
+                    self.rust_inline_args(tokens[i], acc);
+                    i += 1;
+                }
+                "identifier" => {
+                    // Nested macro: `format!(..)`
+                    if kind(i + 1) == "!" {
+                        i += 2;
+                        continue;
+                    }
+                    // Structured field name: `email = %user.email`
+                    if kind(i + 1) == "=" && kind(i + 2) != "=" {
+                        i += 1;
+                        continue;
+                    }
+                    // Collect `a.b(..).c` into segments of (name, call args)
+                    let start = tokens[i].start_byte();
+                    let mut segments: Vec<(Node, Option<Node>)> = Vec::new();
+                    let mut end = i;
+                    loop {
+                        let args = (kind(end + 1) == "token_tree").then(|| tokens[end + 1]);
+                        segments.push((tokens[end], args));
+                        let next = end + 1 + usize::from(args.is_some());
+                        if kind(next) == "." && kind(next + 1) == "identifier" {
+                            end = next + 1;
+                        } else {
+                            end = next;
+                            break;
+                        }
+                    }
+                    self.rust_chain(start, &segments, acc);
+                    i = end;
+                }
+                _ => i += 1,
+            }
+        }
+    }
+
+    /// Evaluate one dotted run, outermost segment first
+    fn rust_chain(&self, start: usize, segments: &[(Node, Option<Node>)], acc: &mut Args) {
+        for (depth, (ident, args)) in segments.iter().enumerate().rev() {
+            let name = self.text(*ident);
+            let span = Span {
+                start,
+                end: args.unwrap_or(*ident).end_byte(),
+            };
+            if args.is_some() && is_sanitizer(name) {
+                return;
+            }
+            if self.names.matches(name) {
+                acc.sensitive(span);
+                return;
+            }
+            if depth > 0 && is_derived_member(name) {
+                return;
+            }
+            if depth > 0 && BODY_MEMBERS.contains(&normalized(name).as_str()) {
+                acc.body(span);
+                return;
+            }
+            if let Some(args) = args {
+                self.rust_tokens(*args, acc);
+            }
+            if depth == 0 && segments.len() == 1 && args.is_none() {
+                if EXCEPTION_NAMES.contains(&name) {
+                    acc.exception = true;
+                } else if self.names.is_object(name) {
+                    acc.object(span);
+                } else if is_body_name(name) {
+                    acc.body(span);
+                }
+            }
+        }
+    }
+
+    /// Inline format captures: `info!("login {email}")`
+    fn rust_inline_args(&self, literal: Node, acc: &mut Args) {
+        let text = self.text(literal);
+        let bytes = text.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] != b'{' {
+                i += 1;
+                continue;
+            }
+            if bytes.get(i + 1) == Some(&b'{') {
+                i += 2;
+                continue;
+            }
+            let name_start = i + 1;
+            let mut name_end = name_start;
+            while name_end < bytes.len()
+                && (bytes[name_end].is_ascii_alphanumeric() || bytes[name_end] == b'_')
+            {
+                name_end += 1;
+            }
+            if name_end > name_start && matches!(bytes.get(name_end), Some(b'}' | b':')) {
+                let name = &text[name_start..name_end];
+                let span = Span {
+                    start: literal.start_byte() + name_start,
+                    end: literal.start_byte() + name_end,
+                };
+                if self.names.matches(name) {
+                    acc.sensitive(span);
+                } else if self.names.is_object(name) {
+                    acc.object(span);
+                }
+            }
+            i = name_end.max(i + 1);
+        }
+    }
+}
@@ -0,0 +1,656 @@
+//! Skew analyzer — detects dependency versions and config values that drift
+//! apart across the packages of a monorepo
+//!
+//! Dependency declarations are read from every `package.json`,
+//! `pyproject.toml` (PEP 621 and Poetry) and `Cargo.toml`, and grouped by
+//! ecosystem and package name. A package declared at more than
+//! `max_versions` distinct versions is flagged at each location that differs
+//! from the most common version (the highest one on a tie). Versions are
+//! compared without range operators, so `^18.2.0` and `18.2.0` agree;
+//! declarations that are not a plain version (`workspace:*`, git URLs,
+//! compound npm ranges) are skipped.
+//!
+//! Each `keys` entry (`tsconfig.json:compilerOptions.strict`) compares one
+//! config value across every file of that name. Files setting a value other
+//! than the majority's are flagged — or all of them when no value has a
+//! majority. `tsconfig.json` may contain comments and trailing commas.
+//!
+//! Packages are named by their directory relative to the repository root, as
+//! for lockfile projects. An `allow` entry (`react@17:packages/legacy-admin`)
+//! takes a declaration or config value out of the comparison entirely.
+//!
+//! Finding prefix: `SKEW-`
+
+use crate::analyzer::{make_finding, Analyzer};
+use crate::config::{RevetConfig, SkewConfig};
+use crate::finding::{Finding, FindingDetails, Severity, VersionRow};
+use crate::lockfile::{default_project_name, Ecosystem};
+use regex::Regex;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Manifests whose dependency declarations are compared
+const MANIFESTS: &[&str] = &["package.json", "pyproject.toml", "Cargo.toml"];
+
+/// `package.json` dependency sections (peer ranges are deliberately loose)
+const NPM_SECTIONS: &[&str] = &["dependencies", "devDependencies", "optionalDependencies"];
+
+/// `Cargo.toml` dependency tables
+const CARGO_SECTIONS: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
+// ── Config entries ────────────────────────────────────────────────────────────
+
+/// A config value compared across same-named files
+/// (`tsconfig.json:compilerOptions.strict`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySpec {
+    /// File name, e.g. `tsconfig.json`
+    pub file: String,
+    /// Dotted key path within the file
+    pub key: String,
+}
+
+impl KeySpec {
+    /// Parse `file:dotted.key`; the file must be a `.json` or `.toml` name
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (file, key) = spec.split_once(':')?;
+        let (file, key) = (file.trim(), key.trim());
+        let supported = file.ends_with(".json") || file.ends_with(".toml");
+        if !supported || file.contains('/') || key.split('.').any(str::is_empty) {
+            return None;
+        }
+        Some(Self {
+            file: file.to_string(),
+            key: key.to_string(),
+        })
+    }
+
+    fn lookup<'v>(&self, doc: &'v Value) -> Option<&'v Value> {
+        self.key
+            .split('.')
+            .try_fold(doc, |value, part| value.get(part))
+    }
+
+    /// Last segment of the key, used to find its line
+    fn leaf(&self) -> &str {
+        self.key.rsplit('.').next().unwrap_or(&self.key)
+    }
+}
+
+/// An intentional exception (`react@17:packages/legacy-admin`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllowEntry {
+    /// Package name, or config key for `keys` values
+    pub name: String,
+    /// Version or value; `17` also matches `17.0.2`
+    pub version: String,
+    /// Package directory the exception is limited to (`None` = every package)
+    pub path: Option<String>,
+}
+
+impl AllowEntry {
+    /// Parse `name@version` or `name@version:path`
+    pub fn parse(entry: &str) -> Option<Self> {
+        // Scoped npm names start with `@`, so split at the last one
+        let at = entry.rfind('@').filter(|&i| i > 0)?;
+        let name = entry[..at].trim();
+        let rest = &entry[at + 1..];
+        let (version, path) = match rest.split_once(':') {
+            Some((version, path)) => (version.trim(), Some(path.trim().trim_end_matches('/'))),
+            None => (rest.trim(), None),
+        };
+        if name.is_empty() || version.is_empty() || path == Some("") {
+            return None;
+        }
+        Some(Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            path: path.map(String::from),
+        })
+    }
+
+    fn matches(&self, name: &str, value: &str, project: &str) -> bool {
+        let version_matches = value == self.version
+            || value
+                .strip_prefix(self.version.as_str())
+                .is_some_and(|rest| rest.starts_with('.'));
+        let path_matches = self.path.as_deref().is_none_or(|path| {
+            project == path
+                || project
+                    .strip_prefix(path)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        });
+        self.name == name && version_matches && path_matches
+    }
+}
+
+// ── Analyzer ──────────────────────────────────────────────────────────────────
+
+/// One package's version of a dependency, or value of a config key
+struct Observation {
+    value: String,
+    project: String,
+    file: PathBuf,
+    line: usize,
+}
+
+/// Declarations of one dependency across the repository
+struct Dependency {
+    /// Name as first written
+    name: String,
+    observations: Vec<Observation>,
+}
+
+/// Analyzer that detects version and config skew across monorepo packages
+pub struct SkewAnalyzer {
+    max_versions: usize,
+    keys: Vec<KeySpec>,
+    allow: Vec<AllowEntry>,
+}
+
+impl SkewAnalyzer {
+    /// Create a skew analyzer with the default settings
+    pub fn new() -> Self {
+        Self::with_config(&SkewConfig::default())
+    }
+
+    /// Create a skew analyzer honouring `[analyzers.skew]`
+    pub fn with_config(config: &SkewConfig) -> Self {
+        Self {
+            max_versions: config.max_versions,
+            keys: config
+                .keys
+                .iter()
+                .filter_map(|k| KeySpec::parse(k))
+                .collect(),
+            allow: config
+                .allow
+                .iter()
+                .filter_map(|a| AllowEntry::parse(a))
+                .collect(),
+        }
+    }
+
+    fn should_scan(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        MANIFESTS.contains(&name) || self.keys.iter().any(|k| k.file == name)
+    }
+
+    fn allowed(&self, name: &str, observation: &Observation) -> bool {
+        self.allow
+            .iter()
+            .any(|a| a.matches(name, &observation.value, &observation.project))
+    }
+
+    fn version_findings(&self, dependency: Dependency) -> Vec<Finding> {
+        let name = dependency.name.as_str();
+        let observations: Vec<Observation> = dependency
+            .observations
+            .into_iter()
+            .filter(|o| !self.allowed(name, o))
+            .collect();
+        let groups = by_value(&observations, compare_versions);
+        if groups.len() <= self.max_versions {
+            return Vec::new();
+        }
+
+        let (majority, majority_uses) = &groups[0];
+        let details = details(&groups);
+        let mut findings = Vec::new();
+        for (value, uses) in &groups[1..] {
+            for o in uses {
+                let message = format!(
+                    "{} wants {} {}, {} {}",
+                    o.project,
+                    name,
+                    value,
+                    others_use(majority_uses.len()),
+                    majority
+                );
+                let suggestion = format!(
+                    "Align {} on {} across packages, or allow the exception with \"{}@{}:{}\" under [analyzers.skew] allow",
+                    name, majority, name, value, o.project
+                );
+                findings.push(Finding {
+                    rule: Some("versions".to_string()),
+                    details: Some(details.clone()),
+                    ..make_finding(
+                        Severity::Warning,
+                        message,
+                        o.file.clone(),
+                        o.line,
+                        Some(suggestion),
+                        None,
+                    )
+                });
+            }
+        }
+        findings
+    }
+
+    fn config_findings(&self, spec: &KeySpec, observations: Vec<Observation>) -> Vec<Finding> {
+        let observations: Vec<Observation> = observations
+            .into_iter()
+            .filter(|o| !self.allowed(&spec.key, o))
+            .collect();
+        let groups = by_value(&observations, |a, b| a.cmp(b));
+        if groups.len() < 2 {
+            return Vec::new();
+        }
+
+        let (majority, majority_uses) = &groups[0];
+        let no_majority = majority_uses.len() == groups[1].1.len();
+        let details = details(&groups);
+        let flagged = if no_majority {
+            &groups[..]
+        } else {
+            &groups[1..]
+        };
+        let mut findings = Vec::new();
+        for (value, uses) in flagged {
+            for o in uses {
+                let (message, suggestion) = if no_majority {
+                    (
+                        format!(
+                            "{} sets {} = {} in {}, and {} files disagree with no majority",
+                            o.project, spec.key, value, spec.file, spec.file
+                        ),
+                        format!(
+                            "Settle on one value of {} across packages, or allow the exceptions under [analyzers.skew] allow",
+                            spec.key
+                        ),
+                    )
+                } else {
+                    (
+                        format!(
+                            "{} sets {} = {} in {}, {} {}",
+                            o.project,
+                            spec.key,
+                            value,
+                            spec.file,
+                            others_use(majority_uses.len()),
+                            majority
+                        ),
+                        format!(
+                            "Set {} = {} like the other packages, or allow the exception with \"{}@{}:{}\" under [analyzers.skew] allow",
+                            spec.key, majority, spec.key, value, o.project
+                        ),
+                    )
+                };
+                findings.push(Finding {
+                    rule: Some("config".to_string()),
+                    details: Some(details.clone()),
+                    ..make_finding(
+                        Severity::Warning,
+                        message,
+                        o.file.clone(),
+                        o.line,
+                        Some(suggestion),
+                        None,
+                    )
+                });
+            }
+        }
+        findings
+    }
+}
+
+impl Default for SkewAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer for SkewAnalyzer {
+    fn name(&self) -> &str {
+        "Monorepo Skew"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "SKEW"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.skew
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some("skew")
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        &["versions", "config"]
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        self.should_scan(path)
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], repo_root: &Path) -> Vec<Finding> {
+        let mut files: Vec<&PathBuf> = files.iter().filter(|f| self.should_scan(f)).collect();
+        files.sort();
+
+        let mut dependencies: BTreeMap<(Ecosystem, String), Dependency> = BTreeMap::new();
+        let mut values: Vec<Vec<Observation>> = self.keys.iter().map(|_| Vec::new()).collect();
+        for file in files {
+            let Some(file_name) = file.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Ok(content) = std::fs::read_to_string(file) else {
+                continue;
+            };
+            let Some(doc) = parse_document(file_name, &content) else {
+                continue;
+            };
+            let project = default_project_name(file.parent().unwrap_or(repo_root), repo_root);
+
+            if MANIFESTS.contains(&file_name) {
+                let mut seen = Vec::new();
+                for (ecosystem, name, version) in declarations(file_name, &doc) {
+                    let key = (ecosystem, ecosystem.normalize_name(&name));
+                    if seen.contains(&key) {
+                        continue;
+                    }
+                    seen.push(key.clone());
+                    let line = key_line(&content, &name);
+                    dependencies
+                        .entry(key)
+                        .or_insert_with(|| Dependency {
+                            name: name.clone(),
+                            observations: Vec::new(),
+                        })
+                        .observations
+                        .push(Observation {
+                            value: version,
+                            project: project.clone(),
+                            file: file.clone(),
+                            line,
+                        });
+                }
+            }
+
+            for (spec, observed) in self.keys.iter().zip(values.iter_mut()) {
+                if spec.file != file_name {
+                    continue;
+                }
+                if let Some(value) = spec.lookup(&doc).and_then(scalar) {
+                    observed.push(Observation {
+                        value,
+                        project: project.clone(),
+                        file: file.clone(),
+                        line: key_line(&content, spec.leaf()),
+                    });
+                }
+            }
+        }
+
+        let mut findings = Vec::new();
+        for dependency in dependencies.into_values() {
+            findings.extend(self.version_findings(dependency));
+        }
+        for (spec, observed) in self.keys.iter().zip(values) {
+            findings.extend(self.config_findings(spec, observed));
+        }
+        findings
+    }
+
+    fn extra_extensions(&self) -> &[&str] {
+        &[".json", ".toml"]
+    }
+}
+
+// ── Reading manifests ─────────────────────────────────────────────────────────
+
+/// Parse a `.json` (comments and trailing commas allowed) or `.toml` file
+fn parse_document(file_name: &str, content: &str) -> Option<Value> {
+    if file_name.ends_with(".json") {
+        serde_json::from_str(&strip_jsonc(content)).ok()
+    } else if file_name.ends_with(".toml") {
+        let doc: toml::Value = toml::from_str(content).ok()?;
+        serde_json::to_value(doc).ok()
+    } else {
+        None
+    }
+}
+
+/// `(ecosystem, name, version)` of every plain-version dependency a
+/// manifest declares
+fn declarations(file_name: &str, doc: &Value) -> Vec<(Ecosystem, String, String)> {
+    let mut out = Vec::new();
+    match file_name {
+        "package.json" => {
+            for section in NPM_SECTIONS {
+                for (name, spec) in entries(doc.get(section)) {
+                    if let Some(version) = spec.as_str().and_then(plain_version) {
+                        out.push((Ecosystem::Npm, name.clone(), version));
+                    }
+                }
+            }
+        }
+        "Cargo.toml" => {
+            let workspace = doc.get("workspace").and_then(|w| w.get("dependencies"));
+            let tables = CARGO_SECTIONS.iter().map(|s| doc.get(s)).chain([workspace]);
+            for table in tables {
+                for (name, spec) in entries(table) {
+                    if let Some(version) = table_version(spec) {
+                        out.push((Ecosystem::Cargo, name.clone(), version));
+                    }
+                }
+            }
+        }
+        "pyproject.toml" => {
+            let project = doc.get("project");
+            let optional = project.and_then(|p| p.get("optional-dependencies"));
+            let lists = std::iter::once(project.and_then(|p| p.get("dependencies")))
+                .chain(entries(optional).map(|(_, list)| Some(list)));
+            for list in lists.flatten().filter_map(Value::as_array) {
+                for spec in list.iter().filter_map(Value::as_str) {
+                    if let Some((name, version)) = pep508(spec) {
+                        out.push((Ecosystem::PyPI, name, version));
+                    }
+                }
+            }
+
+            let poetry = doc.get("tool").and_then(|t| t.get("poetry"));
+            let groups = poetry.and_then(|p| p.get("group"));
+            let tables = [
+                poetry.and_then(|p| p.get("dependencies")),
+                poetry.and_then(|p| p.get("dev-dependencies")),
+            ]
+            .into_iter()
+            .chain(entries(groups).map(|(_, group)| group.get("dependencies")));
+            for table in tables {
+                for (name, spec) in entries(table) {
+                    if name == "python" {
+                        continue;
+                    }
+                    if let Some(version) = table_version(spec) {
+                        out.push((Ecosystem::PyPI, name.clone(), version));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    out
+}
+
+/// Key / value pairs of an optional JSON object
+fn entries(value: Option<&Value>) -> impl Iterator<Item = (&String, &Value)> {
+    value.and_then(Value::as_object).into_iter().flatten()
+}
+
+/// Version of a Cargo or Poetry dependency: `"1.0"` or `{ version = "1.0" }`
+fn table_version(spec: &Value) -> Option<String> {
+    match spec {
+        Value::String(s) => plain_version(s),
+        Value::Object(table) => table.get("version")?.as_str().and_then(plain_version),
+        _ => None,
+    }
+}
+
+/// The version in a single-version requirement (`^18.2.0`, `~=2.31`, `1.0`)
+fn plain_version(spec: &str) -> Option<String> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(r"^(?:~=|==|>=|\^|~|=)?\s*v?(\d+(?:\.\d+)*(?:[-+][0-9A-Za-z.\-]+)?)$").unwrap()
+    });
+    let caps = re.captures(spec.trim())?;
+    Some(caps[1].to_string())
+}
+
+/// Name and first version bound of a PEP 508 requirement
+/// (`requests[socks]>=2.31,<3; python_version > "3.8"` → `requests`, `2.31`)
+fn pep508(spec: &str) -> Option<(String, String)> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(
+            r"^\s*([A-Za-z0-9][A-Za-z0-9._\-]*)\s*(?:\[[^\]]*\])?\s*\(?\s*(?:==|~=|>=)\s*(\d[0-9A-Za-z.+\-]*)",
+        )
+        .unwrap()
+    });
+    let caps = re.captures(spec)?;
+    Some((caps[1].to_string(), caps[2].to_string()))
+}
+
+/// A config value as shown in findings; tables and arrays are not compared
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Bool(_) | Value::Number(_) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// 1-based line of the first entry keyed by `name`, or 1 if not found
+///
+/// Matches `"name": ...` in JSON, `name = ...` in TOML and `"name>=1.0"` in
+/// PEP 508 lists.
+fn key_line(content: &str, name: &str) -> usize {
+    let quoted = format!("\"{}\"", name);
+    content
+        .lines()
+        .position(|line| {
+            let json_key = line
+                .match_indices(&quoted)
+                .any(|(i, _)| line[i + quoted.len()..].trim_start().starts_with(':'));
+            let start =
+                line.trim_start_matches(|c: char| c.is_whitespace() || "\"'[{,".contains(c));
+            json_key
+                || start.strip_prefix(name).is_some_and(|rest| {
+                    !rest.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                })
+        })
+        .map_or(1, |i| i + 1)
+}
+
+/// Drop `//` and `/* */` comments and trailing commas from JSON, leaving
+/// strings untouched
+pub(crate) fn strip_jsonc(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    if c == '\n' {
+                        out.push('\n');
+                    }
+                    prev = c;
+                }
+            }
+            (',', _) => {
+                let rest = chars.clone().find(|c| !c.is_whitespace());
+                if !matches!(rest, Some('}' | ']')) {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// ── Reporting ─────────────────────────────────────────────────────────────────
+
+/// Observations grouped by value, most common first; ties go to the value
+/// `compare` ranks highest
+fn by_value(
+    observations: &[Observation],
+    compare: fn(&str, &str) -> Ordering,
+) -> Vec<(&str, Vec<&Observation>)> {
+    let mut groups: BTreeMap<&str, Vec<&Observation>> = BTreeMap::new();
+    for o in observations {
+        groups.entry(o.value.as_str()).or_default().push(o);
+    }
+    let mut groups: Vec<(&str, Vec<&Observation>)> = groups.into_iter().collect();
+    groups.sort_by(|(a, a_uses), (b, b_uses)| {
+        b_uses.len().cmp(&a_uses.len()).then_with(|| compare(b, a))
+    });
+    groups
+}
+
+/// Every value in use and the packages using it, most used first
+fn details(groups: &[(&str, Vec<&Observation>)]) -> FindingDetails {
+    FindingDetails {
+        version_table: groups
+            .iter()
+            .map(|(value, uses)| VersionRow {
+                version: value.to_string(),
+                used_by: uses.iter().map(|o| o.project.clone()).collect(),
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// `1 other package uses` / `3 other packages use`
+fn others_use(count: usize) -> String {
+    if count == 1 {
+        "1 other package uses".to_string()
+    } else {
+        format!("{} other packages use", count)
+    }
+}
+
+/// Compare versions by their numeric segments, then as strings
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let numbers = |v: &str| -> Vec<u64> {
+        v.split(['.', '-', '+'])
+            .map_while(|part| part.parse().ok())
+            .collect()
+    };
+    numbers(a).cmp(&numbers(b)).then_with(|| a.cmp(b))
+}
@@ -6,7 +6,8 @@
 
 use crate::analyzer::{make_finding, Analyzer};
 use crate::config::RevetConfig;
-use crate::finding::{Finding, FixKind, Severity};
+use crate::finding::{Finding, FindingDetails, FixKind, InsertContext, Severity, SuggestedCode};
+use crate::suggestion;
 use regex::Regex;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
@@ -17,6 +18,8 @@ struct SqlPattern {
     regex: Regex,
     severity: Severity,
     suggestion: &'static str,
+    /// Builds the parameterized call from the matched line
+    code: Option<fn(&Path, &str) -> Option<SuggestedCode>>,
     /// If non-empty, only scan files with one of these extensions (without leading dot)
     extensions: &'static [&'static str],
 }
@@ -28,7 +31,7 @@ fn patterns() -> &'static [SqlPattern] {
         // Shared SQL keyword group
         let kw = r"(?:SELECT|INSERT|UPDATE|DELETE|DROP|ALTER|CREATE|REPLACE|MERGE|TRUNCATE|EXEC)\b";
         // Common DB execution method names
-        let exec = r"(?:execute|executemany|executescript|raw|rawquery|query|prepare)";
+        let exec = EXEC;
 
         vec![
             // ── Error: interpolation inside DB execution calls ──────────
@@ -40,7 +43,8 @@ fn patterns() -> &'static [SqlPattern] {
                 regex: Regex::new(&format!(r#"\.(?:objects\.raw|text)\s*\(\s*f["'].*{kw}"#))
                     .unwrap(),
                 severity: Severity::Error,
-                suggestion: "Use parameterized queries: .objects.raw('SELECT ... WHERE id = %s', [id])",
+                suggestion: "Use a parameterized query: pass the values in a params list with %s placeholders",
+                code: Some(raw_fstring),
                 extensions: &[],
             },
             // Pattern 2: f-string SQL in DB call — .execute(f"...SQL...")
@@ -48,7 +52,8 @@ fn patterns() -> &'static [SqlPattern] {
                 name: "f-string SQL in database call",
                 regex: Regex::new(&format!(r#"\.{exec}\s*\(\s*f["'].*{kw}"#)).unwrap(),
                 severity: Severity::Error,
-                suggestion: "Use parameterized queries: .execute('SELECT ... WHERE id = ?', (id,))",
+                suggestion: "Use a parameterized query: pass the values as parameters with ? placeholders",
+                code: Some(exec_fstring),
                 extensions: &[],
             },
             // Pattern 3: String concat SQL in DB call — .execute("...SQL..." + var)
@@ -57,6 +62,7 @@ fn patterns() -> &'static [SqlPattern] {
                 regex: Regex::new(&format!(r#"\.{exec}\s*\(\s*["'].*{kw}.*["']\s*\+"#)).unwrap(),
                 severity: Severity::Error,
                 suggestion: "Use parameterized queries instead of string concatenation",
+                code: None,
                 extensions: &[],
             },
             // Pattern 4: .format() SQL in DB call — .execute("...SQL...".format())
@@ -68,6 +74,7 @@ fn patterns() -> &'static [SqlPattern] {
                 .unwrap(),
                 severity: Severity::Error,
                 suggestion: "Use parameterized queries instead of .format()",
+                code: None,
                 extensions: &[],
             },
             // Pattern 5: % format SQL in DB call — .execute("...SQL..." % var)
@@ -79,6 +86,7 @@ fn patterns() -> &'static [SqlPattern] {
                     .unwrap(),
                 severity: Severity::Error,
                 suggestion: "Use parameterized queries instead of %-formatting",
+                code: Some(exec_percent),
                 extensions: &[],
             },
             // Pattern 6: Template literal SQL in DB call — .query(`...SQL...${var}`)
@@ -88,6 +96,7 @@ fn patterns() -> &'static [SqlPattern] {
                     .unwrap(),
                 severity: Severity::Error,
                 suggestion: "Use parameterized queries instead of template literals",
+                code: Some(exec_template),
                 extensions: &[],
             },
             // ── Error: Rust format! macro with SQL ───────────────────────
@@ -97,6 +106,7 @@ fn patterns() -> &'static [SqlPattern] {
                 regex: Regex::new(&format!(r#"format!\s*\(\s*"[^"]*{kw}[^"]*\{{"#)).unwrap(),
                 severity: Severity::Error,
                 suggestion: "Use a parameterized query library (e.g. sqlx query! macro or prepared statements)",
+                code: None,
                 extensions: &["rs"],
             },
             // ── Error: Go fmt.Sprintf with SQL ───────────────────────────
@@ -105,7 +115,8 @@ fn patterns() -> &'static [SqlPattern] {
                 name: "Go fmt.Sprintf building SQL string",
                 regex: Regex::new(&format!(r#"fmt\.Sprintf\s*\(\s*"[^"]*{kw}[^"]*%"#)).unwrap(),
                 severity: Severity::Error,
-                suggestion: "Use db.Query/db.Exec with ? placeholders: db.Query(\"SELECT ... WHERE id = ?\", id)",
+                suggestion: "Use db.Query/db.Exec with ? placeholders and pass the values as arguments",
+                code: None,
                 extensions: &["go"],
             },
             // ── Error: Java String.format / + concatenation with SQL ─────
@@ -115,6 +126,7 @@ fn patterns() -> &'static [SqlPattern] {
                 regex: Regex::new(&format!(r#"String\.format\s*\(\s*"[^"]*{kw}"#)).unwrap(),
                 severity: Severity::Error,
                 suggestion: "Use PreparedStatement with ? placeholders instead of String.format()",
+                code: None,
                 extensions: &["java"],
             },
             // Pattern 10: Java string + concatenation in SQL context — "SELECT..." + var
@@ -123,6 +135,7 @@ fn patterns() -> &'static [SqlPattern] {
                 regex: Regex::new(&format!(r#""[^"]*{kw}[^"]*"\s*\+\s*\w"#)).unwrap(),
                 severity: Severity::Error,
                 suggestion: "Use PreparedStatement with ? placeholders instead of string concatenation",
+                code: None,
                 extensions: &["java"],
             },
             // ── Warning: standalone SQL strings with interpolation ──────
@@ -133,6 +146,7 @@ fn patterns() -> &'static [SqlPattern] {
                 regex: Regex::new(&format!(r#"=\s*f["'].*{kw}.*\{{"#)).unwrap(),
                 severity: Severity::Warning,
                 suggestion: "Use parameterized queries: pass variables as parameters, not in the query string",
+                code: None,
                 extensions: &[],
             },
             // Pattern 12: String concat SQL — "...SQL..." + var
@@ -141,6 +155,7 @@ fn patterns() -> &'static [SqlPattern] {
                 regex: Regex::new(&format!(r#"["'].*{kw}.*["']\s*\+\s*\w"#)).unwrap(),
                 severity: Severity::Warning,
                 suggestion: "Use parameterized queries instead of string concatenation",
+                code: None,
                 extensions: &[],
             },
             // Pattern 13: .format() SQL string — "...SQL...{}".format()
@@ -149,6 +164,7 @@ fn patterns() -> &'static [SqlPattern] {
                 regex: Regex::new(&format!(r#"["'].*{kw}.*["']\s*\.format\s*\("#)).unwrap(),
                 severity: Severity::Warning,
                 suggestion: "Use parameterized queries instead of .format()",
+                code: None,
                 extensions: &[],
             },
             // Pattern 14: % format SQL string — "...SQL...%s" % var
@@ -157,6 +173,7 @@ fn patterns() -> &'static [SqlPattern] {
                 regex: Regex::new(&format!(r#"["'].*{kw}.*["']\s*%\s*\w"#)).unwrap(),
                 severity: Severity::Warning,
                 suggestion: "Use parameterized queries instead of %-formatting",
+                code: None,
                 extensions: &[],
             },
             // Pattern 15: Template literal SQL — var = `...SQL...${}`
@@ -165,12 +182,158 @@ fn patterns() -> &'static [SqlPattern] {
                 regex: Regex::new(&format!(r#"`[^`]*{kw}[^`]*\$\{{[^`]*`"#)).unwrap(),
                 severity: Severity::Warning,
                 suggestion: "Use parameterized queries instead of template literals",
+                code: None,
                 extensions: &[],
             },
         ]
     })
 }
 
+/// Database execution method names
+const EXEC: &str = r"(?:execute|executemany|executescript|raw|rawquery|query|prepare)";
+
+/// How a parameterized call passes its values
+#[derive(Clone, Copy)]
+enum Params {
+    Tuple,
+    List,
+}
+
+impl Params {
+    fn render(self, values: &[String]) -> String {
+        match (self, values) {
+            (Params::Tuple, [value]) => format!("({},)", value),
+            (Params::Tuple, _) => format!("({})", values.join(", ")),
+            (Params::List, _) => format!("[{}]", values.join(", ")),
+        }
+    }
+}
+
+/// `.objects.raw(f"... {id}")` → `.objects.raw("... %s", [id])`
+fn raw_fstring(path: &Path, line: &str) -> Option<SuggestedCode> {
+    static CALL: OnceLock<Regex> = OnceLock::new();
+    let call = CALL.get_or_init(|| {
+        Regex::new(
+            r#"\.objects\.raw\s*\(\s*(?P<literal>f(?:"(?P<dq>[^"]*)"|'(?P<sq>[^']*)'))\s*\)"#,
+        )
+        .unwrap()
+    });
+    interpolated(path, line, call, "%s", Params::List)
+}
+
+/// `.execute(f"... {id}")` → `.execute("... ?", (id,))`
+fn exec_fstring(path: &Path, line: &str) -> Option<SuggestedCode> {
+    static CALL: OnceLock<Regex> = OnceLock::new();
+    let call = CALL.get_or_init(|| {
+        Regex::new(&format!(
+            r#"\.{EXEC}\s*\(\s*(?P<literal>f(?:"(?P<dq>[^"]*)"|'(?P<sq>[^']*)'))\s*\)"#
+        ))
+        .unwrap()
+    });
+    interpolated(path, line, call, "?", Params::Tuple)
+}
+
+/// `` .query(`... ${id}`) `` → `` .query(`... ?`, [id]) ``
+fn exec_template(path: &Path, line: &str) -> Option<SuggestedCode> {
+    static CALL: OnceLock<Regex> = OnceLock::new();
+    let call = CALL.get_or_init(|| {
+        Regex::new(&format!(
+            r#"\.{EXEC}\s*\(\s*(?P<literal>`(?P<bt>[^`]*)`)\s*\)"#
+        ))
+        .unwrap()
+    });
+    interpolated(path, line, call, "?", Params::List)
+}
+
+/// `.execute("... %s" % id)` → `.execute("... %s", (id,))`
+fn exec_percent(path: &Path, line: &str) -> Option<SuggestedCode> {
+    static CALL: OnceLock<Regex> = OnceLock::new();
+    let call = CALL.get_or_init(|| {
+        Regex::new(&format!(
+            r#"\.{EXEC}\s*\(\s*(?P<literal>"[^"]*"|'[^']*')\s*%\s*(?P<args>[\w.]+)\s*\)"#
+        ))
+        .unwrap()
+    });
+    let caps = call.captures(line)?;
+    let literal = caps.name("literal")?;
+    let args = caps.name("args")?;
+    // Only `%s` placeholders carry over unchanged
+    let placeholders = literal.as_str().matches("%s").count();
+    if placeholders == 0 || literal.as_str().matches('%').count() != placeholders {
+        return None;
+    }
+    // A lone name for several placeholders is the tuple itself
+    let params = if placeholders > 1 {
+        args.as_str().to_string()
+    } else {
+        format!("({},)", args.as_str())
+    };
+    let rewritten = format!(
+        "{}{}, {}{}",
+        &line[..literal.start()],
+        literal.as_str(),
+        params,
+        &line[args.end()..]
+    );
+    suggestion::code(path, rewritten.trim(), InsertContext::Line)
+}
+
+/// The line with the interpolating literal `call` matched replaced by a
+/// plain one with `placeholder`s, followed by the interpolated values
+fn interpolated(
+    path: &Path,
+    line: &str,
+    call: &Regex,
+    placeholder: &str,
+    params: Params,
+) -> Option<SuggestedCode> {
+    let caps = call.captures(line)?;
+    let literal = caps.name("literal")?;
+    let (body, open, quote) = if let Some(body) = caps.name("dq") {
+        (body.as_str(), "{", "\"")
+    } else if let Some(body) = caps.name("sq") {
+        (body.as_str(), "{", "'")
+    } else {
+        (caps.name("bt")?.as_str(), "${", "`")
+    };
+    // A literal `?` or `%` would be taken for a placeholder
+    if body.contains(&placeholder[..1]) {
+        return None;
+    }
+
+    let mut query = String::new();
+    let mut values = Vec::new();
+    let mut rest = body;
+    while let Some(at) = rest.find(open) {
+        query.push_str(&rest[..at]);
+        let after = &rest[at + open.len()..];
+        let end = after.find('}')?;
+        let value = after[..end].trim();
+        // Escaped braces, format specs, conversions and nested braces
+        if value.is_empty() || value.contains(['{', ':', '!']) {
+            return None;
+        }
+        values.push(value.to_string());
+        query.push_str(placeholder);
+        rest = &after[end + 1..];
+    }
+    query.push_str(rest);
+    if values.is_empty() || (open == "{" && query.contains(['{', '}'])) {
+        return None;
+    }
+
+    let rewritten = format!(
+        "{}{}{}{}, {}{}",
+        &line[..literal.start()],
+        quote,
+        query,
+        quote,
+        params.render(&values),
+        &line[literal.end()..]
+    );
+    suggestion::code(path, rewritten.trim(), InsertContext::Line)
+}
+
 /// Binary file extensions to skip
 const BINARY_EXTENSIONS: &[&str] = &[
     "png", "jpg", "jpeg", "gif", "bmp", "ico", "svg", "webp", "woff", "woff2", "ttf", "eot", "otf",
@@ -214,11 +377,13 @@ impl SqlInjectionAnalyzer {
 
     /// Scan a single file for SQL injection patterns
     fn scan_file(path: &Path) -> Vec<Finding> {
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return Vec::new(),
-        };
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::scan_source(path, &content),
+            Err(_) => Vec::new(),
+        }
+    }
 
+    fn scan_source(path: &Path, content: &str) -> Vec<Finding> {
         let file_ext = path
             .extension()
             .and_then(|e| e.to_str())
@@ -241,14 +406,22 @@ impl SqlInjectionAnalyzer {
                     continue;
                 }
                 if pat.regex.is_match(line) {
-                    findings.push(make_finding(
-                        pat.severity,
-                        format!("Possible SQL injection: {}", pat.name),
-                        path.to_path_buf(),
-                        line_num + 1,
-                        Some(pat.suggestion.to_string()),
-                        Some(FixKind::Suggestion),
-                    ));
+                    findings.push(Finding {
+                        rule: Some("sql_injection".to_string()),
+                        suggestion_code: pat.code.and_then(|code| code(path, line)),
+                        details: Some(FindingDetails {
+                            query_text: Some(line.trim().to_string()),
+                            ..Default::default()
+                        }),
+                        ..make_finding(
+                            pat.severity,
+                            format!("Possible SQL injection: {}", pat.name),
+                            path.to_path_buf(),
+                            line_num + 1,
+                            Some(pat.suggestion.to_string()),
+                            Some(FixKind::Suggestion),
+                        )
+                    });
                     break;
                 }
             }
@@ -277,6 +450,22 @@ impl Analyzer for SqlInjectionAnalyzer {
         config.modules.security
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("security")
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        &["sql_injection"]
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Self::should_scan(path)
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
     fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
         let mut findings = Vec::new();
 
@@ -289,4 +478,11 @@ impl Analyzer for SqlInjectionAnalyzer {
 
         findings
     }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        if !Self::should_scan(path) {
+            return Vec::new();
+        }
+        Self::scan_source(path, content)
+    }
 }
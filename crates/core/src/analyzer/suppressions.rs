@@ -0,0 +1,455 @@
+//! Suppression comment audit (`SUPP`) — lints `revet-ignore` comments themselves
+//!
+//! Runs after inline suppression, over every analyzed file, and reports:
+//! - `unknown_rule` — tokens naming no finding prefix, module or sub-rule,
+//!   with a did-you-mean suggestion. Imported rules (`EXT-<tool>-<rule>`)
+//!   are always known
+//! - `malformed` — directives that suppress nothing (or less than written)
+//!   because of their syntax: `revet_ignore`, `revet-ignore:SEC`,
+//!   `revet-ignore SEC, SQL`, ... A bad `until=` date or an unknown option
+//!   is a warning: the rules still apply
+//! - `expired` — rules whose `until=` date has passed, which no longer
+//!   suppress anything
+//! - `useless` — suppressions that matched no finding in this run. Only
+//!   reported for full-repo runs, since diff runs see fewer findings, and
+//!   only for rules whose analyzer ran. `--fix` removes the comment, or just
+//!   the unused rule from a multi-rule comment. When a finding the rule
+//!   matches is just past what the comment covers, the suggestion names the
+//!   line it likely meant instead, or `:block`, and there is no fix.
+//!
+//! A `revet-ignore SUPP` (or `suppressions`) covering the comment's line
+//! silences these findings; `*` does not.
+
+use super::{catalog_finding, registry, rules, AnalyzerDispatcher};
+use crate::config::RevetConfig;
+use crate::finding::{Finding, FixKind, Severity};
+use crate::i18n::Msg;
+use crate::msg;
+use crate::suppress::SuppressedFinding;
+use crate::suppress::{
+    parse_directives_in, resolve_suppressions_at, Directive, DirectiveDefect, DirectiveUsage,
+    Misplaced, RuleOptions,
+};
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+/// `[modules]` name of the audit
+pub const MODULE: &str = "suppressions";
+
+/// Sub-rules, selectable as `suppressions:<rule>`
+pub const RULES: &[&str] = &["unknown_rule", "malformed", "expired", "useless"];
+
+/// Tokens that silence audit findings
+fn silences_audit(token: &str) -> bool {
+    token == "SUPP" || token == MODULE || token.starts_with("suppressions:")
+}
+
+/// Known suppression tokens, and which of them could have matched a
+/// finding in this run
+pub struct SuppressionAudit {
+    known: BTreeSet<String>,
+    live: HashSet<String>,
+    /// `YYYY-MM-DD`, for `until=` expiry
+    today: String,
+}
+
+impl SuppressionAudit {
+    /// Collect the reserved prefixes and the modules and sub-rules of every
+    /// built-in analyzer; the ones `dispatcher` runs under `config` are live
+    pub fn new(dispatcher: &AnalyzerDispatcher, config: &RevetConfig) -> Self {
+        let mut known: BTreeSet<String> = registry::Registry::new()
+            .prefixes()
+            .map(|(prefix, _)| prefix.to_string())
+            .collect();
+        for (module, rules) in rules::catalog() {
+            known.insert(module.to_string());
+            known.extend(rules.iter().map(|r| format!("{}:{}", module, r)));
+        }
+        // Custom rules with their own prefix
+        known.extend(dispatcher.prefixes().map(String::from));
+        // Configured sub-rules (custom secret patterns), also by stable ID
+        for analyzer in &dispatcher.analyzers {
+            let Some(module) = analyzer.module() else {
+                continue;
+            };
+            for rule in analyzer.rules().iter().filter(|r| r.starts_with("custom-")) {
+                known.insert(format!("{}:{}", module, rule));
+                known.insert(format!("{}-{}", analyzer.finding_prefix(), rule));
+            }
+        }
+
+        let mut live: HashSet<String> = HashSet::from(["*".to_string()]);
+        let content = dispatcher
+            .analyzers
+            .iter()
+            .filter(|a| a.is_enabled(config))
+            .map(|a| (a.finding_prefix(), a.module(), a.rules()));
+        let graph = dispatcher
+            .graph_analyzers
+            .iter()
+            .filter(|a| a.is_enabled(config))
+            .map(|a| (a.finding_prefix(), a.module(), a.rules()));
+        for (prefix, module, sub_rules) in content.chain(graph) {
+            if !rules::selects_any(module, sub_rules, config) {
+                continue;
+            }
+            live.insert(prefix.to_string());
+            if let Some(module) = module {
+                live.insert(module.to_string());
+                let selection = config.rule_selection(module);
+                for rule in sub_rules.iter().filter(|r| selection.allows(r)) {
+                    live.insert(format!("{}:{}", module, rule));
+                    if rule.starts_with("custom-") {
+                        live.insert(format!("{}-{}", prefix, rule));
+                    }
+                }
+            }
+        }
+        Self {
+            known,
+            live,
+            today: crate::baseline::today(),
+        }
+    }
+
+    /// Audit the directives of `files`. `usage` comes from the inline
+    /// suppression pass; `report_useless` is set for full-repo runs.
+    ///
+    /// Returns `(findings, silenced)`, the latter being findings a
+    /// `revet-ignore SUPP` comment covers.
+    pub fn audit(
+        &self,
+        files: &[PathBuf],
+        usage: &DirectiveUsage,
+        report_useless: bool,
+    ) -> (Vec<Finding>, Vec<Finding>) {
+        let mut findings = Vec::new();
+        let mut silenced = Vec::new();
+        for file in files {
+            let Ok(content) = std::fs::read_to_string(file) else {
+                continue;
+            };
+            if !content.contains("revet") {
+                continue;
+            }
+            let directives = parse_directives_in(file, &content);
+            let silencing: Vec<_> = resolve_suppressions_at(file, &content, &[], &self.today)
+                .into_iter()
+                .filter(|s| s.rules.iter().any(|t| silences_audit(t)))
+                .map(|s| s.covers)
+                .collect();
+            for directive in &directives {
+                for finding in self.check(file, directive, usage, report_useless) {
+                    if silencing.iter().any(|lines| lines.contains(&finding.line)) {
+                        silenced.push(finding);
+                    } else {
+                        findings.push(finding);
+                    }
+                }
+            }
+        }
+        (findings, silenced)
+    }
+
+    fn check(
+        &self,
+        file: &Path,
+        directive: &Directive,
+        usage: &DirectiveUsage,
+        report_useless: bool,
+    ) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        if let Some(defect) = &directive.defect {
+            findings.push(at(
+                malformed(file, directive, defect),
+                directive.column,
+                directive.end_column,
+            ));
+        }
+
+        for options in &directive.options {
+            if options.rules.is_empty() || !options.is_expired(&self.today) {
+                continue;
+            }
+            findings.push(at(
+                expired(file, directive, options),
+                directive.tokens[options.rules.start].1,
+                options.end_column,
+            ));
+        }
+
+        let mut unused = Vec::new();
+        let mut any_used = false;
+        for (i, (token, column)) in directive.tokens.iter().enumerate() {
+            let end_column = column + token.chars().count();
+            if !self.is_known(token) {
+                let suggestion = match self.did_you_mean(token) {
+                    Some(candidate) => msg!("suppressions-did-you-mean", candidate = candidate),
+                    None => msg!("suppressions-unknown-rule.suggestion"),
+                };
+                let finding = Finding {
+                    rule: Some("unknown_rule".to_string()),
+                    ..catalog_finding(
+                        Severity::Warning,
+                        msg!("suppressions-unknown-rule", token = token),
+                        file.to_path_buf(),
+                        directive.line,
+                        Some(suggestion),
+                        None,
+                    )
+                };
+                findings.push(at(finding, *column, end_column));
+            } else if directive.is_expired(i, &self.today) {
+                // Reported as expired above
+                continue;
+            } else if usage.is_used(file, directive.line, token) {
+                any_used = true;
+            } else if self.live.contains(token) {
+                unused.push((token, *column, end_column));
+            } else {
+                // Rules that could not match this run count as in use
+                any_used = true;
+            }
+        }
+        // A malformed directive is reported as such; removing it could
+        // drop rules its author meant to write
+        if !report_useless || unused.is_empty() || directive.defect.is_some() {
+            return findings;
+        }
+
+        let unknown = directive.tokens.iter().any(|(t, _)| !self.is_known(t));
+        let misplaced = |token: &str| usage.misplaced(file, directive.line, token);
+        if !any_used && !unknown && unused.len() == directive.tokens.len() {
+            let rules: Vec<&str> = directive.tokens.iter().map(|(t, _)| t.as_str()).collect();
+            let nearest = rules
+                .iter()
+                .filter_map(|t| misplaced(t))
+                .min_by_key(|m| m.line.abs_diff(directive.line));
+            let finding = useless(
+                file,
+                directive.line,
+                msg!("suppressions-useless", rules = rules.join(" ")),
+                msg!("suppressions-useless.suggestion"),
+                None,
+                nearest,
+            );
+            findings.push(at(finding, directive.column, directive.end_column));
+        } else {
+            for (token, column, end_column) in unused {
+                let finding = useless(
+                    file,
+                    directive.line,
+                    msg!("suppressions-useless-rule", token = token),
+                    msg!("suppressions-useless-rule.suggestion", token = token),
+                    Some(token.clone()),
+                    misplaced(token),
+                );
+                findings.push(at(finding, column, end_column));
+            }
+        }
+        findings
+    }
+
+    fn is_known(&self, token: &str) -> bool {
+        token == "*" || self.known.contains(token) || is_imported_rule(token)
+    }
+
+    /// Closest known token: the prefix of a finding ID (`SEC-001` → `SEC`),
+    /// a case-insensitive match, or the nearest name within a small edit
+    /// distance
+    fn did_you_mean(&self, token: &str) -> Option<&str> {
+        if let Some((prefix, _)) = token.split_once('-') {
+            if let Some(known) = self.known.get(prefix) {
+                return Some(known);
+            }
+        }
+        let lower = token.to_lowercase().replace('-', "_");
+        let max = (token.chars().count() / 3).max(1);
+        self.known
+            .iter()
+            .map(|k| (edit_distance(&lower, &k.to_lowercase()), k))
+            .filter(|(d, _)| *d <= max)
+            .min_by_key(|(d, _)| *d)
+            .map(|(_, k)| k.as_str())
+    }
+}
+
+/// `EXT-<tool>-<rule>`: rules of imported reports are not known in advance
+fn is_imported_rule(token: &str) -> bool {
+    token
+        .strip_prefix(crate::import::PREFIX)
+        .and_then(|rest| rest.strip_prefix('-'))
+        .is_some_and(|rest| {
+            rest.split_once('-')
+                .is_some_and(|(tool, rule)| !tool.is_empty() && !rule.is_empty())
+        })
+}
+
+/// Set a finding's column range
+fn at(finding: Finding, column: usize, end_column: usize) -> Finding {
+    Finding {
+        column: Some(column),
+        end_column: Some(end_column),
+        ..finding
+    }
+}
+
+fn malformed(file: &Path, directive: &Directive, defect: &DirectiveDefect) -> Finding {
+    let mut severity = Severity::Error;
+    let (message, suggestion) = match defect {
+        DirectiveDefect::UnknownKeyword(keyword) => (
+            msg!("suppressions-unknown-keyword", keyword = keyword),
+            msg!("suppressions-unknown-keyword.suggestion"),
+        ),
+        DirectiveDefect::MissingSpace(token) => (
+            msg!("suppressions-missing-space", token = token),
+            msg!("suppressions-missing-space.suggestion", token = token),
+        ),
+        DirectiveDefect::NoRules => (
+            msg!("suppressions-no-rules"),
+            msg!("suppressions-no-rules.suggestion"),
+        ),
+        DirectiveDefect::Separator { separator, ignored } => {
+            let applied: Vec<&str> = directive.tokens.iter().map(|(t, _)| t.as_str()).collect();
+            let fixed: Vec<&str> = applied
+                .iter()
+                .copied()
+                .chain(ignored.iter().map(String::as_str))
+                .collect();
+            (
+                msg!(
+                    "suppressions-separator",
+                    separator = separator,
+                    applied = applied.join(" "),
+                    ignored = ignored.join(" ")
+                ),
+                msg!("suppressions-separator.suggestion", fixed = fixed.join(" ")),
+            )
+        }
+        // The rules still apply, so this is only a warning
+        DirectiveDefect::InvalidDate(value) => {
+            severity = Severity::Warning;
+            (
+                msg!("suppressions-invalid-date", value = value),
+                msg!("suppressions-invalid-date.suggestion"),
+            )
+        }
+        DirectiveDefect::UnknownOption(option) => {
+            severity = Severity::Warning;
+            (
+                msg!("suppressions-unknown-option", option = option),
+                msg!("suppressions-unknown-option.suggestion"),
+            )
+        }
+    };
+    Finding {
+        rule: Some("malformed".to_string()),
+        ..catalog_finding(
+            severity,
+            message,
+            file.to_path_buf(),
+            directive.line,
+            Some(suggestion),
+            None,
+        )
+    }
+}
+
+fn expired(file: &Path, directive: &Directive, options: &RuleOptions) -> Finding {
+    let rules: Vec<&str> = directive.tokens[options.rules.clone()]
+        .iter()
+        .map(|(t, _)| t.as_str())
+        .collect();
+    let until = options.until.as_deref().unwrap_or_default();
+    let message = match &options.reason {
+        Some(reason) => msg!(
+            "suppressions-expired-reason",
+            rules = rules.join(" "),
+            until = until,
+            reason = reason
+        ),
+        None => msg!(
+            "suppressions-expired",
+            rules = rules.join(" "),
+            until = until
+        ),
+    };
+    Finding {
+        rule: Some("expired".to_string()),
+        ..catalog_finding(
+            Severity::Info,
+            message,
+            file.to_path_buf(),
+            directive.line,
+            Some(msg!("suppressions-expired.suggestion")),
+            None,
+        )
+    }
+}
+
+/// A useless-suppression finding. With a `misplaced` finding, the
+/// suggestion points at it instead of removing the comment.
+fn useless(
+    file: &Path,
+    line: usize,
+    message: Msg,
+    suggestion: Msg,
+    token: Option<String>,
+    misplaced: Option<Misplaced>,
+) -> Finding {
+    let (suggestion, fix) = match misplaced {
+        Some(Misplaced { line, block: true }) => {
+            (msg!("suppressions-misplaced-block", line = line), None)
+        }
+        Some(Misplaced { line, .. }) => (msg!("suppressions-misplaced", line = line), None),
+        None => (suggestion, Some(FixKind::RemoveSuppression { token })),
+    };
+    Finding {
+        rule: Some("useless".to_string()),
+        ..catalog_finding(
+            Severity::Info,
+            message,
+            file.to_path_buf(),
+            line,
+            Some(suggestion),
+            fix,
+        )
+    }
+}
+
+/// Edit distance between two strings, by character, counting an adjacent
+/// transposition (`SCE` → `SEC`) as one edit
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Convert silenced audit findings to suppressed findings
+pub(crate) fn silenced(findings: Vec<Finding>) -> Vec<SuppressedFinding> {
+    findings
+        .into_iter()
+        .map(|finding| SuppressedFinding {
+            finding,
+            reason: "inline".to_string(),
+        })
+        .collect()
+}
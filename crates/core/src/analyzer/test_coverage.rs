@@ -100,6 +100,10 @@ impl GraphAnalyzer for TestCoverageAnalyzer {
         config.modules.test_coverage
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("test_coverage")
+    }
+
     fn analyze_graph(&self, graph: &CodeGraph, _config: &RevetConfig) -> Vec<Finding> {
         // 1. Partition File nodes into test files and source files
         let mut test_files: Vec<std::path::PathBuf> = Vec::new();
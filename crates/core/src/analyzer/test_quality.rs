@@ -0,0 +1,216 @@
+//! Test-quality analyzer — flaky and toothless tests
+//!
+//! Only test code is scanned: files the [`CodeKinds`] of `[files]` classify
+//! as `test`. Sub-rules:
+//!
+//! - `sleep`: fixed waits (`time.sleep(1)`, `Thread.sleep`, `time.Sleep`,
+//!   `page.waitForTimeout(500)`, `cy.wait(1000)`, `Task.Delay`), which make
+//!   tests slow and flaky (see [`lines`])
+//! - `no_assertions`: tests whose body asserts nothing, found with
+//!   tree-sitter in Python, JavaScript/TypeScript, Java, Go, Rust and Ruby
+//!   (see [`assertions`]). Medium confidence: a helper may assert on the
+//!   test's behalf
+//! - `stale_skip`: skipped or disabled tests (`@pytest.mark.skip`,
+//!   `it.skip`, `@Disabled`, `#[ignore]`, `xit`) whose marker `git blame`
+//!   dates more than `skip_max_age_days` ago (see [`blame`]). Uncommitted
+//!   markers and files outside a repository are never stale
+//! - `broad_exception`: assertions that any exception satisfies
+//!   (`pytest.raises(Exception)`, `assertThrows(Exception.class, ...)`,
+//!   `toThrow()`, a bare `#[should_panic]`)
+//!
+//! Each sub-rule's severity comes from `[analyzers.test_quality.severity]`
+//! (defaults: warning, warning, info, warning).
+//!
+//! Finding prefix: `TEST-`
+
+pub mod assertions;
+pub mod blame;
+pub mod lines;
+
+use crate::analyzer::{catalog_finding, Analyzer};
+use crate::code_kind::{CodeKind, CodeKinds};
+use crate::config::{FilesConfig, RevetConfig, TestQualityConfig};
+use crate::finding::{Confidence, Finding, Severity};
+use crate::msg;
+use crate::parser::grammar::Family;
+use assertions::AssertionPatterns;
+use blame::Ages;
+use lines::Kind;
+use std::path::{Path, PathBuf};
+
+/// Module name of the test-quality analyzer
+pub const MODULE: &str = "test_quality";
+
+/// Sub-rules of the test-quality analyzer
+pub const RULES: &[&str] = &["sleep", "no_assertions", "stale_skip", "broad_exception"];
+
+/// Analyzer that reports flaky and toothless tests
+pub struct TestQualityAnalyzer {
+    code_kinds: CodeKinds,
+    patterns: AssertionPatterns,
+    skip_max_age_days: u64,
+    sleep: Severity,
+    no_assertions: Severity,
+    stale_skip: Severity,
+    broad_exception: Severity,
+}
+
+impl TestQualityAnalyzer {
+    /// Create a test-quality analyzer with the default settings
+    pub fn new() -> Self {
+        Self::with_config(&TestQualityConfig::default())
+    }
+
+    /// Create a test-quality analyzer honouring `[analyzers.test_quality]`
+    pub fn with_config(config: &TestQualityConfig) -> Self {
+        let severity =
+            |rule: &str, default: Severity| match config.severity.get(rule).map(String::as_str) {
+                Some("error") => Severity::Error,
+                Some("warning") => Severity::Warning,
+                Some("info") => Severity::Info,
+                _ => default,
+            };
+        Self {
+            code_kinds: CodeKinds::default(),
+            patterns: AssertionPatterns::new(&config.assertion_patterns),
+            skip_max_age_days: config.skip_max_age_days,
+            sleep: severity("sleep", Severity::Warning),
+            no_assertions: severity("no_assertions", Severity::Warning),
+            stale_skip: severity("stale_skip", Severity::Info),
+            broad_exception: severity("broad_exception", Severity::Warning),
+        }
+    }
+
+    /// Tell test code apart with the `test_paths`/`script_paths` of `[files]`
+    pub fn with_files(mut self, files: &FilesConfig) -> Self {
+        self.code_kinds = CodeKinds::from_config(files);
+        self
+    }
+
+    fn scan_source(&self, path: &Path, content: &str, repo_root: &Path) -> Vec<Finding> {
+        let Some(family) = Family::of(path) else {
+            return Vec::new();
+        };
+        if self.code_kinds.classify(path, repo_root, content) != CodeKind::Test {
+            return Vec::new();
+        }
+        let hits = lines::scan(family, content);
+        let ages = hits
+            .iter()
+            .any(|hit| hit.kind == Kind::Skip)
+            .then(|| Ages::of(path, content, repo_root))
+            .flatten();
+        let mut findings: Vec<Finding> = hits
+            .iter()
+            .filter_map(|hit| self.hit(path, hit, ages.as_ref()))
+            .collect();
+        findings.extend(
+            assertions::without_assertions(path, content, &self.patterns)
+                .into_iter()
+                .map(|test| Finding {
+                    rule: Some("no_assertions".to_string()),
+                    confidence: Confidence::Medium,
+                    ..catalog_finding(
+                        self.no_assertions,
+                        msg!("test-no-assertions", test = test.name),
+                        path.to_path_buf(),
+                        test.line,
+                        Some(msg!("test-no-assertions.suggestion")),
+                        None,
+                    )
+                }),
+        );
+        findings.sort_by_key(|f| f.line);
+        findings
+    }
+
+    fn hit(&self, path: &Path, hit: &lines::Hit, ages: Option<&Ages>) -> Option<Finding> {
+        let text = hit.text.as_str();
+        let (rule, severity, message, suggestion) = match hit.kind {
+            Kind::Sleep => (
+                "sleep",
+                self.sleep,
+                msg!("test-sleep", call = text),
+                msg!("test-sleep.suggestion"),
+            ),
+            Kind::BroadException => (
+                "broad_exception",
+                self.broad_exception,
+                msg!("test-broad-exception", assertion = text),
+                msg!("test-broad-exception.suggestion"),
+            ),
+            Kind::Skip => {
+                let days = ages?.days(hit.line)?;
+                if days <= self.skip_max_age_days {
+                    return None;
+                }
+                (
+                    "stale_skip",
+                    self.stale_skip,
+                    msg!("test-stale-skip", marker = text, days = days),
+                    msg!("test-stale-skip.suggestion"),
+                )
+            }
+        };
+        Some(Finding {
+            rule: Some(rule.to_string()),
+            ..catalog_finding(
+                severity,
+                message,
+                path.to_path_buf(),
+                hit.line,
+                Some(suggestion),
+                None,
+            )
+        })
+    }
+}
+
+impl Default for TestQualityAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer for TestQualityAnalyzer {
+    fn name(&self) -> &str {
+        "Test quality"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "TEST"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.test_quality
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some(MODULE)
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        Family::of(path).is_some()
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], repo_root: &Path) -> Vec<Finding> {
+        files
+            .iter()
+            .filter(|f| self.accepts(f))
+            .filter_map(|f| Some(self.scan_source(f, &std::fs::read_to_string(f).ok()?, repo_root)))
+            .flatten()
+            .collect()
+    }
+
+    fn analyze_source(&self, path: &Path, content: &str, repo_root: &Path) -> Vec<Finding> {
+        self.scan_source(path, content, repo_root)
+    }
+}
@@ -0,0 +1,375 @@
+//! Tests that assert nothing
+//!
+//! Python, JavaScript/TypeScript, Java, Go, Rust and Ruby tests are found
+//! with tree-sitter:
+//!
+//! - Python: `test*` functions and methods
+//! - JavaScript/TypeScript: `it(...)`/`test(...)` callbacks
+//! - Java: `@Test`, `@ParameterizedTest` and `@RepeatedTest` methods
+//! - Go: `Test*` functions taking a `*testing.T`
+//! - Rust: `#[test]`, `#[tokio::test]`, `#[rstest]` and `#[test_case]`
+//!   functions
+//! - Ruby: RSpec `it`/`specify`/`example` blocks and minitest `test_*`
+//!   methods
+//!
+//! A test asserts when its body contains an assertion statement (`assert`
+//! in Python and Java, `?` in Rust) or calls an assertion: a name starting
+//! with `assert`, `expect`, `verify`, `should`, `must`, `check`, `fail` or
+//! `refute` as a word (`assertEqual`, `expect_that`, not `checkout`), a
+//! per-language one (`pytest.raises`, Go's `t.Errorf`/`t.Fatal`, testify's
+//! `require`, Rust's `unwrap`/`panic!`, minitest's `flunk`) or one matching
+//! `[analyzers.test_quality] assertion_patterns`. A receiver counts too, so
+//! `assert.equal(...)` and `require.NoError(...)` assert. Java's
+//! `@Test(expected = ...)` and Rust's `#[should_panic]` are assertions of
+//! their own. A test that skips itself (`t.Skip`, `pytest.skip`, RSpec's
+//! `skip`) is left to `stale_skip`.
+
+use crate::parser::grammar::{language_for, Family, Grammar};
+use regex::Regex;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Name prefixes of assertion calls, compared ignoring case
+const PREFIXES: &[&str] = &[
+    "assert",
+    "expect",
+    "verify",
+    "should",
+    "must",
+    "check",
+    "fail",
+    "refute",
+    "xctassert",
+    "prop_assert",
+    "debug_assert",
+];
+
+/// JavaScript test functions
+const JS_TESTS: &[&str] = &["it", "test", "it.only", "test.only", "specify"];
+
+/// Ruby example methods
+const RUBY_TESTS: &[&str] = &["it", "specify", "example", "scenario"];
+
+/// Java test annotations
+const JAVA_TESTS: &[&str] = &["Test", "ParameterizedTest", "RepeatedTest"];
+
+/// Rust test attributes (last path segment)
+const RUST_TESTS: &[&str] = &["test", "rstest", "test_case"];
+
+/// Assertion names of the language beyond [`PREFIXES`], and the calls by
+/// which a test skips itself
+fn assertions(family: Family) -> &'static [&'static str] {
+    match family {
+        Family::Python => &["raises", "warns", "deprecated_call", "skip", "skipTest"],
+        Family::Go => &[
+            "Error", "Errorf", "Fatal", "Fatalf", "Fail", "FailNow", "require", "Skip", "Skipf",
+            "SkipNow",
+        ],
+        Family::Rust => &["unwrap", "panic", "unreachable"],
+        Family::Ruby => &["flunk", "skip", "pending"],
+        _ => &[],
+    }
+}
+
+/// The grammar of `path`, if its tests are checked
+pub fn grammar(path: &Path) -> Option<Grammar> {
+    language_for(path).filter(|g| {
+        matches!(
+            g.family,
+            Family::Python | Family::Js | Family::Java | Family::Go | Family::Rust | Family::Ruby
+        )
+    })
+}
+
+/// Compiled `assertion_patterns`
+#[derive(Debug, Clone, Default)]
+pub struct AssertionPatterns(Vec<Regex>);
+
+impl AssertionPatterns {
+    /// Patterns that do not compile are skipped (config validation reports
+    /// them)
+    pub fn new(patterns: &[String]) -> Self {
+        Self(patterns.iter().filter_map(|p| Regex::new(p).ok()).collect())
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        self.0.iter().any(|re| re.is_match(name))
+    }
+}
+
+/// A test without assertions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Test {
+    /// Function name, or the description of an `it`/`test` block
+    pub name: String,
+    pub line: usize,
+}
+
+/// Every test in one source that asserts nothing, in source order
+pub fn without_assertions(path: &Path, content: &str, patterns: &AssertionPatterns) -> Vec<Test> {
+    let Some(grammar) = grammar(path) else {
+        return Vec::new();
+    };
+    let Some(tree) = grammar.parse(content) else {
+        return Vec::new();
+    };
+    let mut scanner = Scanner {
+        family: grammar.family,
+        source: content,
+        patterns,
+        tests: Vec::new(),
+    };
+    scanner.walk(tree.root_node());
+    scanner.tests
+}
+
+/// A test found in the tree
+struct Found<'t> {
+    name: String,
+    line: usize,
+    body: Node<'t>,
+    /// Asserted by an annotation or attribute
+    declared: bool,
+}
+
+struct Scanner<'a> {
+    family: Family,
+    source: &'a str,
+    patterns: &'a AssertionPatterns,
+    tests: Vec<Test>,
+}
+
+impl<'a> Scanner<'a> {
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.source.as_bytes()).unwrap_or("")
+    }
+
+    fn walk(&mut self, node: Node) {
+        if let Some(test) = self.test(node) {
+            if !test.declared && !self.asserts(test.body) {
+                self.tests.push(Test {
+                    name: test.name,
+                    line: test.line,
+                });
+            }
+            return;
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.walk(child);
+        }
+    }
+
+    /// The test `node` declares, if any
+    fn test<'t>(&self, node: Node<'t>) -> Option<Found<'t>> {
+        match (self.family, node.kind()) {
+            (Family::Python, "function_definition") => {
+                let name = node.child_by_field_name("name")?;
+                if !self.text(name).starts_with("test") {
+                    return None;
+                }
+                Some(self.found(name, node.child_by_field_name("body")?, false))
+            }
+            (Family::Js, "call_expression") => {
+                let callee = self.text(node.child_by_field_name("function")?);
+                if !JS_TESTS.contains(&callee) {
+                    return None;
+                }
+                let args = node.child_by_field_name("arguments")?;
+                let mut cursor = args.walk();
+                let args: Vec<Node> = args.named_children(&mut cursor).collect();
+                let body = *args.iter().find(|a| {
+                    matches!(
+                        a.kind(),
+                        "arrow_function" | "function_expression" | "function"
+                    )
+                })?;
+                Some(Found {
+                    name: args
+                        .first()
+                        .filter(|a| matches!(a.kind(), "string" | "template_string"))
+                        .map_or(callee.to_string(), |a| unquote(self.text(*a)).to_string()),
+                    line: node.start_position().row + 1,
+                    body,
+                    declared: false,
+                })
+            }
+            (Family::Java, "method_declaration") => {
+                let mut cursor = node.walk();
+                let modifiers = node
+                    .children(&mut cursor)
+                    .find(|c| c.kind() == "modifiers")?;
+                let mut cursor = modifiers.walk();
+                let annotation = modifiers.children(&mut cursor).find(|a| {
+                    matches!(a.kind(), "marker_annotation" | "annotation")
+                        && a.child_by_field_name("name")
+                            .is_some_and(|n| JAVA_TESTS.contains(&self.text(n)))
+                })?;
+                let declared = annotation
+                    .child_by_field_name("arguments")
+                    .is_some_and(|args| self.text(args).contains("expected"));
+                let name = node.child_by_field_name("name")?;
+                Some(self.found(name, node.child_by_field_name("body")?, declared))
+            }
+            (Family::Go, "function_declaration") => {
+                let name = node.child_by_field_name("name")?;
+                let params = node.child_by_field_name("parameters")?;
+                if !self.text(name).starts_with("Test") || !self.text(params).contains("testing.T")
+                {
+                    return None;
+                }
+                Some(self.found(name, node.child_by_field_name("body")?, false))
+            }
+            (Family::Rust, "function_item") => {
+                let attributes = self.rust_attributes(node);
+                if !attributes
+                    .iter()
+                    .any(|a| RUST_TESTS.contains(&a.rsplit("::").next().unwrap_or(a)))
+                {
+                    return None;
+                }
+                let declared = attributes.contains(&"should_panic");
+                let name = node.child_by_field_name("name")?;
+                Some(self.found(name, node.child_by_field_name("body")?, declared))
+            }
+            (Family::Ruby, "call") => {
+                let method = self.text(node.child_by_field_name("method")?);
+                if node.child_by_field_name("receiver").is_some() || !RUBY_TESTS.contains(&method) {
+                    return None;
+                }
+                let body = node.child_by_field_name("block")?;
+                let name = node
+                    .child_by_field_name("arguments")
+                    .and_then(|args| args.named_child(0))
+                    .filter(|a| a.kind() == "string")
+                    .map_or(method.to_string(), |a| unquote(self.text(a)).to_string());
+                Some(Found {
+                    name,
+                    line: node.start_position().row + 1,
+                    body,
+                    declared: false,
+                })
+            }
+            (Family::Ruby, "method") => {
+                let name = node.child_by_field_name("name")?;
+                self.text(name)
+                    .starts_with("test_")
+                    .then(|| self.found(name, node, false))
+            }
+            _ => None,
+        }
+    }
+
+    fn found<'t>(&self, name: Node, body: Node<'t>, declared: bool) -> Found<'t> {
+        Found {
+            name: self.text(name).to_string(),
+            line: name.start_position().row + 1,
+            body,
+            declared,
+        }
+    }
+
+    /// Paths of the attributes on a Rust item: `test`, `tokio::test`,
+    /// `should_panic`
+    fn rust_attributes(&self, node: Node) -> Vec<&'a str> {
+        let mut attributes = Vec::new();
+        let mut sibling = node.prev_named_sibling();
+        while let Some(s) = sibling {
+            match s.kind() {
+                "attribute_item" => {
+                    let text = self.text(s).trim_start_matches("#[");
+                    let end = text.find(['(', ']', ' ', '=']).unwrap_or(text.len());
+                    attributes.push(&text[..end]);
+                }
+                "line_comment" | "block_comment" => {}
+                _ => break,
+            }
+            sibling = s.prev_named_sibling();
+        }
+        attributes
+    }
+
+    /// Whether anything under `node` is an assertion
+    fn asserts(&self, node: Node) -> bool {
+        let found = match node.kind() {
+            "assert_statement" | "try_expression" => true,
+            "call" | "call_expression" | "method_invocation" | "macro_invocation" => {
+                self.callee(node).is_some_and(|c| self.is_assertion(&c))
+            }
+            _ => false,
+        };
+        if found {
+            return true;
+        }
+        let mut cursor = node.walk();
+        let nested = node.children(&mut cursor).any(|child| self.asserts(child));
+        nested
+    }
+
+    /// Callee text without whitespace: `self.assertEqual`, `t.Errorf`,
+    /// `expect(x).toBe`, `assert_eq`
+    fn callee(&self, node: Node) -> Option<String> {
+        let text = match node.kind() {
+            "macro_invocation" => self.text(node.child_by_field_name("macro")?).to_string(),
+            "method_invocation" => {
+                let name = self.text(node.child_by_field_name("name")?);
+                match node.child_by_field_name("object") {
+                    Some(object) => format!("{}.{}", self.text(object), name),
+                    None => name.to_string(),
+                }
+            }
+            "call" if self.family == Family::Ruby => {
+                let method = self.text(node.child_by_field_name("method")?);
+                match node.child_by_field_name("receiver") {
+                    Some(receiver) => format!("{}.{}", self.text(receiver), method),
+                    None => method.to_string(),
+                }
+            }
+            _ => self.text(node.child_by_field_name("function")?).to_string(),
+        };
+        Some(text.chars().filter(|c| !c.is_whitespace()).collect())
+    }
+
+    /// Whether a call of `callee` asserts. Every segment of a plain path
+    /// counts (`assert.equal`); otherwise only the method called
+    /// (`toBe` in `expect(x).toBe`).
+    fn is_assertion(&self, callee: &str) -> bool {
+        let plain = callee
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | ':' | '$'));
+        let mut segments = callee.split(['.', ':']).filter(|s| !s.is_empty());
+        let check = |name: &str| {
+            PREFIXES.iter().any(|p| starts_word(name, p))
+                || assertions(self.family).contains(&name)
+                || self.patterns.matches(name)
+        };
+        if plain {
+            segments.any(check)
+        } else {
+            segments.next_back().is_some_and(check)
+        }
+    }
+}
+
+/// Whether `name` starts with the word `prefix`, ignoring case: the prefix
+/// is followed by the end, `_`, or an uppercase letter or digit
+fn starts_word(name: &str, prefix: &str) -> bool {
+    let Some(head) = name.get(..prefix.len()) else {
+        return false;
+    };
+    head.eq_ignore_ascii_case(prefix)
+        && name[prefix.len()..]
+            .chars()
+            .next()
+            .is_none_or(|c| !c.is_ascii_lowercase())
+}
+
+/// Strip quotes
+fn unquote(text: &str) -> &str {
+    for quote in ["\"", "'", "`"] {
+        if let Some(inner) = text.strip_prefix(quote).and_then(|t| t.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    text
+}
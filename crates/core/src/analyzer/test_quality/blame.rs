@@ -0,0 +1,59 @@
+//! How long lines have been in a file, from `git blame`
+
+use git2::{Oid, Repository};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+/// When each line of a file was last changed, by blame against `HEAD`
+pub struct Ages {
+    /// Author time (seconds since the epoch) per line; `None` for lines not
+    /// committed yet
+    times: Vec<Option<i64>>,
+    now: i64,
+}
+
+impl Ages {
+    /// Blame `path` with `content` as its current contents. `None` when the
+    /// file is not in a repository or has no history.
+    pub fn of(path: &Path, content: &str, repo_root: &Path) -> Option<Self> {
+        let repo = Repository::discover(repo_root).ok()?;
+        let workdir = repo.workdir()?.canonicalize().ok()?;
+        let absolute = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            repo_root.join(path)
+        };
+        let absolute = absolute.canonicalize().unwrap_or(absolute);
+        let relative = absolute.strip_prefix(&workdir).ok()?;
+        let committed = repo.blame_file(relative, None).ok()?;
+        let blame = committed.blame_buffer(content.as_bytes()).ok()?;
+        // Hunks of a buffer blame carry no signatures; read the commits
+        let mut authored: HashMap<Oid, Option<i64>> = HashMap::new();
+        let times = (1..=content.lines().count())
+            .map(|line| {
+                let id = blame.get_line(line)?.final_commit_id();
+                if id.is_zero() {
+                    return None;
+                }
+                *authored.entry(id).or_insert_with(|| {
+                    let commit = repo.find_commit(id).ok()?;
+                    let time = commit.author().when().seconds();
+                    Some(time)
+                })
+            })
+            .collect();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        Some(Self { times, now })
+    }
+
+    /// Whole days since `line` was committed; `None` for uncommitted lines
+    pub fn days(&self, line: usize) -> Option<u64> {
+        let time = (*self.times.get(line.checked_sub(1)?)?)?;
+        Some(((self.now - time).max(0) / DAY_SECS) as u64)
+    }
+}
@@ -0,0 +1,249 @@
+//! Line patterns of the test-quality analyzer: fixed waits, skip markers and
+//! broad exception assertions
+//!
+//! Each language family has its own patterns, matched against code lines
+//! (comment lines are skipped). A wait only counts when its duration is a
+//! literal or a name, so `asyncio.sleep(0)` (yield to the event loop) and
+//! `cy.wait("@alias")` (wait for a request) are left alone. Go's `t.Skip`
+//! only marks a skipped test as the first statement of a `Test` function,
+//! and Ruby's `skip` only without an `if`/`unless` modifier; elsewhere they
+//! are conditional skips.
+
+use crate::parser::grammar::Family;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// What a line pattern reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Sleep,
+    Skip,
+    BroadException,
+}
+
+/// One pattern match
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hit {
+    pub kind: Kind,
+    pub line: usize,
+    /// The waiting call (`time.sleep`, `cy.wait`) for a wait, the trimmed
+    /// line otherwise
+    pub text: String,
+}
+
+struct Patterns {
+    sleep: Vec<Regex>,
+    skip: Vec<Regex>,
+    broad: Vec<Regex>,
+}
+
+/// A duration worth reporting: a non-zero number or a name
+const DURATION: &str = r"(?:0*[1-9][\d_]*(?:\.\d+)?|0*\.\d*[1-9]\d*|[A-Za-z_][\w.]*)";
+
+fn compile(patterns: &[&str]) -> Vec<Regex> {
+    patterns
+        .iter()
+        .map(|p| Regex::new(&p.replace("{D}", DURATION)).expect("valid test-quality regex"))
+        .collect()
+}
+
+fn patterns(family: Family) -> &'static Patterns {
+    // Kotlin, Groovy and Scala tests use the JVM libraries Java's do
+    let family = match family {
+        Family::Kotlin | Family::Groovy | Family::Scala => Family::Java,
+        family => family,
+    };
+    static TABLE: OnceLock<Vec<(Family, Patterns)>> = OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        let entry = |family, sleep: &[&str], skip: &[&str], broad: &[&str]| {
+            (
+                family,
+                Patterns {
+                    sleep: compile(sleep),
+                    skip: compile(skip),
+                    broad: compile(broad),
+                },
+            )
+        };
+        vec![
+            entry(
+                Family::Python,
+                &[
+                    r"\basyncio\.sleep\s*\(\s*{D}",
+                    r"\b(?:time\.)?sleep\s*\(\s*{D}",
+                ],
+                &[
+                    r"^\s*@pytest\.mark\.skip\b(?:\s*\(|\s*$)",
+                    r"^\s*@unittest\.skip\b(?:\s*\(|\s*$)",
+                ],
+                &[
+                    r"\bpytest\.raises\s*\(\s*(?:Base)?Exception\s*[,)]",
+                    r"\bassertRaises(?:Regex)?\s*\(\s*(?:Base)?Exception\s*[,)]",
+                ],
+            ),
+            entry(
+                Family::Js,
+                &[
+                    r"new\s+Promise\s*\(.*=>\s*setTimeout\s*\(",
+                    r"\b\w+\.waitForTimeout\s*\(\s*{D}",
+                    r"\bcy\.wait\s*\(\s*\d",
+                    r"\bawait\s+(?:sleep|delay|wait)\s*\(\s*{D}",
+                ],
+                &[r"^\s*(?:x(?:it|describe|test)|(?:it|describe|test)\.skip)\s*\("],
+                &[
+                    r"\.toThrow(?:Error)?\s*\(\s*(?:Error\s*)?\)",
+                    r"\bassert\.(?:throws|rejects)\s*\(.*,\s*Error\s*\)",
+                ],
+            ),
+            entry(
+                Family::Java,
+                &[
+                    r"\bThread\.sleep\s*\(",
+                    r"\bTimeUnit\.\w+\.sleep\s*\(",
+                    r"\bSystemClock\.sleep\s*\(",
+                ],
+                &[r"^\s*@(?:Disabled|Ignore)\b"],
+                &[
+                    r"\bassertThrows\s*\(\s*(?:Exception|Throwable|RuntimeException)(?:\.class|::class)",
+                    r"\bassertThatExceptionOfType\s*\(\s*(?:Exception|Throwable|RuntimeException)(?:\.class|::class)",
+                    r"\.isInstanceOf\s*\(\s*(?:Exception|Throwable)(?:\.class|::class)",
+                    r"@Test\s*\(\s*expected\s*=\s*(?:Exception|Throwable|RuntimeException)\.class",
+                    r"\b(?:assertFailsWith|assertThrows|shouldThrow)\s*<\s*(?:Exception|Throwable|RuntimeException)\s*>",
+                ],
+            ),
+            entry(
+                Family::Go,
+                &[r"\btime\.Sleep\s*\("],
+                &[r"^\s*t\.Skip(?:f|Now)?\s*\("],
+                &[],
+            ),
+            entry(
+                Family::Rust,
+                &[
+                    r"\bthread::sleep\s*\(",
+                    r"\btokio::time::sleep\s*\(",
+                    r"(?:^|[^:\w])sleep\s*\(\s*(?:std::time::)?Duration::",
+                ],
+                &[r"^\s*#\[ignore(?:\s*=.*)?\]"],
+                &[r"^\s*#\[should_panic\]"],
+            ),
+            entry(
+                Family::Ruby,
+                &[r"^\s*(?:Kernel\.)?sleep(?:\s+|\s*\(\s*){D}"],
+                &[
+                    r"^\s*x(?:it|describe|context|specify)\b",
+                    r"^\s*skip(?:\s|\(|$)",
+                ],
+                &[
+                    r"\braise_error\s*\(\s*(?:StandardError|Exception)\s*\)",
+                    r"\braise_error\s*(?:$|\})",
+                    r"\bassert_raises\s*\(?\s*(?:StandardError|Exception)\b",
+                ],
+            ),
+            entry(
+                Family::CSharp,
+                &[r"\bThread\.Sleep\s*\(", r"\bTask\.Delay\s*\(\s*{D}"],
+                &[
+                    r"\[\s*(?:Fact|Theory)\s*\(.*\bSkip\s*=",
+                    r"\[\s*Ignore\b",
+                ],
+                &[
+                    r"\bAssert\.Throws(?:Any)?(?:Async)?\s*<\s*(?:System\.)?Exception\s*>",
+                    r"\.Throw(?:Async)?\s*<\s*(?:System\.)?Exception\s*>",
+                    r"\[\s*ExpectedException\s*\(\s*typeof\s*\(\s*(?:System\.)?Exception\s*\)",
+                ],
+            ),
+            entry(
+                Family::Php,
+                &[r"\b(?:u)?sleep\s*\(\s*{D}"],
+                &[],
+                &[r"\bexpectException\s*\(\s*\\?(?:Exception|Throwable)::class"],
+            ),
+            entry(
+                Family::Swift,
+                &[
+                    r"\bsleep\s*\(\s*{D}",
+                    r"\bThread\.sleep\s*\(",
+                    r"\bTask\.sleep\s*\(",
+                ],
+                &[r"\bthrow\s+XCTSkip\s*\("],
+                &[],
+            ),
+        ]
+    });
+    &table
+        .iter()
+        .find(|(f, _)| *f == family)
+        .expect("every family has patterns")
+        .1
+}
+
+/// Whether a trimmed line is a comment (`#[...]` is a Rust or PHP attribute)
+fn is_comment(line: &str) -> bool {
+    line.starts_with("//")
+        || line.starts_with("/*")
+        || line.starts_with('*')
+        || line.starts_with('#') && !line.starts_with("#[")
+}
+
+/// Every match in `content`, in line order
+pub fn scan(family: Family, content: &str) -> Vec<Hit> {
+    let patterns = patterns(family);
+    let mut hits = Vec::new();
+    let mut previous = "";
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || is_comment(trimmed) {
+            continue;
+        }
+        let groups = [
+            (Kind::Sleep, &patterns.sleep),
+            (Kind::Skip, &patterns.skip),
+            (Kind::BroadException, &patterns.broad),
+        ];
+        for (kind, regexes) in groups {
+            let Some(m) = regexes.iter().find_map(|re| re.find(line)) else {
+                continue;
+            };
+            if kind == Kind::Skip && !is_unconditional(family, trimmed, previous) {
+                continue;
+            }
+            let text = match kind {
+                Kind::Sleep => callee(m.as_str()),
+                _ => trimmed,
+            };
+            hits.push(Hit {
+                kind,
+                line: i + 1,
+                text: text.to_string(),
+            });
+        }
+        previous = trimmed;
+    }
+    hits
+}
+
+/// The call a wait pattern matched: the path before its last `(`
+/// (`setTimeout` in `new Promise((r) => setTimeout(`), or its first word
+/// (`sleep` in Ruby's `sleep 1`)
+fn callee(matched: &str) -> &str {
+    let head = match matched.rfind('(') {
+        Some(i) => matched[..i].trim_end(),
+        None => matched.split_whitespace().next().unwrap_or(matched),
+    };
+    let start = head
+        .rfind(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '.' | ':')))
+        .map_or(0, |i| i + 1);
+    &head[start..]
+}
+
+/// Whether a skip on `line` always applies: Go's `t.Skip` opens a `Test`
+/// function (`func TestX(t *testing.T) {` on the line before) and Ruby's
+/// `skip` has no `if`/`unless` modifier
+fn is_unconditional(family: Family, line: &str, previous: &str) -> bool {
+    match family {
+        Family::Go => previous.starts_with("func Test") && previous.ends_with('{'),
+        Family::Ruby => !line.contains(" if ") && !line.contains(" unless "),
+        _ => true,
+    }
+}
@@ -209,6 +209,10 @@ impl Analyzer for ToolchainAnalyzer {
         config.modules.toolchain
     }
 
+    fn module(&self) -> Option<&'static str> {
+        Some("toolchain")
+    }
+
     /// The toolchain analyzer works at the repo level, not file-by-file.
     /// It reads well-known paths directly from `repo_root` regardless of
     /// which files were passed in (so it works even on diff-only runs).
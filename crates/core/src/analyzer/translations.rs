@@ -0,0 +1,394 @@
+//! Translations analyzer — detects translation keys missing from a locale
+//! and catalog keys no code looks up
+//!
+//! Catalogs are the files matching a `catalogs` pattern (see [`catalog`]);
+//! the pattern's `{locale}` and `{namespace}` placeholders say which locale
+//! and namespace each file holds. Lookups are the literal keys passed to the
+//! translation calls of the configured frameworks (see [`lookups`]).
+//!
+//! - `missing_key`: a looked-up key that one or more locales do not define,
+//!   reported at the lookup with the locales lacking it
+//! - `unused_key`: a catalog key no lookup names, reported once at its
+//!   default-locale definition
+//! - `missing_namespace`: a namespace file the default locale has and another
+//!   locale lacks; its keys are then not reported as missing one by one
+//! - `dynamic_key`: a lookup built at runtime, which cannot be checked. Its
+//!   literal prefix (`errors.` in `` t(`errors.${code}`) ``) keeps matching
+//!   catalog keys from being reported as unused
+//!
+//! A key `ns:key` (i18next) is looked up in namespace `ns` only; an
+//! unqualified key resolves in any namespace, and also as `ns.key` against
+//! the `ns` file. Plural forms (`items_one`, `items_other`) count as `items`.
+//!
+//! Finding prefix: `I18N-`
+
+pub mod catalog;
+pub mod lookups;
+
+use crate::analyzer::{catalog_finding, Analyzer};
+use crate::config::{I18nConfig, RevetConfig};
+use crate::finding::{Confidence, Finding, Severity};
+use crate::msg;
+use catalog::{Catalog, CatalogPattern};
+use lookups::{Key, Lookup, LookupTable};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Dynamic key expressions longer than this are shortened in messages
+const MAX_EXPR: usize = 60;
+
+/// Analyzer that checks translation catalogs against the keys code looks up
+pub struct TranslationsAnalyzer {
+    patterns: Vec<CatalogPattern>,
+    default_locale: String,
+    table: LookupTable,
+}
+
+impl TranslationsAnalyzer {
+    /// Create a translations analyzer with the default settings
+    pub fn new() -> Self {
+        Self::with_config(&I18nConfig::default())
+    }
+
+    /// Create a translations analyzer honouring `[analyzers.i18n]`
+    pub fn with_config(config: &I18nConfig) -> Self {
+        Self {
+            patterns: config
+                .catalogs
+                .iter()
+                .filter_map(|p| CatalogPattern::parse(p))
+                .collect(),
+            default_locale: config.default_locale.clone(),
+            table: LookupTable::new(&config.frameworks, &config.functions),
+        }
+    }
+
+    /// `(locale, namespace)` of a catalog file
+    fn catalog_of(&self, path: &Path, repo_root: &Path) -> Option<(String, Option<String>)> {
+        let rel = path.strip_prefix(repo_root).unwrap_or(path);
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        self.patterns.iter().find_map(|p| p.captures(&rel))
+    }
+}
+
+impl Default for TranslationsAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer for TranslationsAnalyzer {
+    fn name(&self) -> &str {
+        "Translations"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "I18N"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.i18n
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some("i18n")
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        &[
+            "missing_key",
+            "unused_key",
+            "missing_namespace",
+            "dynamic_key",
+        ]
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        // The repository root is unknown here; any ancestor may be it
+        lookups::supports(path)
+            || path
+                .ancestors()
+                .skip(1)
+                .any(|root| self.catalog_of(path, root).is_some())
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], repo_root: &Path) -> Vec<Finding> {
+        let mut catalogs = Vec::new();
+        let mut sources = Vec::new();
+        for file in files {
+            if let Some((locale, namespace)) = self.catalog_of(file, repo_root) {
+                catalogs.extend(Catalog::read(file.clone(), locale, namespace));
+            } else if lookups::supports(file) {
+                sources.push(file);
+            }
+        }
+        // Without catalogs there is nothing to check lookups against
+        if catalogs.is_empty() {
+            return Vec::new();
+        }
+        catalogs.sort_by(|a, b| a.file.cmp(&b.file));
+
+        let mut sites: Vec<(PathBuf, Lookup)> = Vec::new();
+        for file in sources {
+            let Ok(content) = std::fs::read_to_string(file) else {
+                continue;
+            };
+            for lookup in lookups::scan(file, &content, &self.table) {
+                sites.push((file.clone(), lookup));
+            }
+        }
+
+        let index = Index::new(&catalogs, &self.default_locale);
+        let mut findings = index.missing_namespaces();
+        let mut used = Used::default();
+        let mut reported = HashSet::new();
+        for (file, lookup) in &sites {
+            match &lookup.key {
+                Key::Literal(key) => {
+                    let (namespace, key) = index.split_namespace(key);
+                    used.literal(namespace, key, &index.namespaces);
+                    if !reported.insert((file.clone(), lookup.line, key.to_string())) {
+                        continue;
+                    }
+                    let missing = index.missing_from(namespace, key);
+                    if !missing.is_empty() {
+                        findings.push(missing_key(file, lookup.line, key, &missing));
+                    }
+                }
+                Key::Dynamic { prefix, expr } => {
+                    used.prefixes.push(prefix.clone());
+                    findings.push(dynamic_key(file, lookup.line, expr));
+                }
+            }
+        }
+        findings.extend(index.unused(&used));
+        findings
+    }
+
+    fn extra_extensions(&self) -> &[&str] {
+        &[".json", ".yaml", ".yml", ".po"]
+    }
+}
+
+// ── Resolution ────────────────────────────────────────────────────────────────
+
+/// `(namespace, key)` of a catalog entry
+type KeyId<'c> = (Option<&'c str>, &'c str);
+
+/// Catalogs grouped for lookups
+struct Index<'c> {
+    catalogs: &'c [Catalog],
+    locales: BTreeSet<&'c str>,
+    namespaces: BTreeSet<&'c str>,
+    default_locale: &'c str,
+}
+
+impl<'c> Index<'c> {
+    fn new(catalogs: &'c [Catalog], default_locale: &'c str) -> Self {
+        Self {
+            catalogs,
+            locales: catalogs.iter().map(|c| c.locale.as_str()).collect(),
+            namespaces: catalogs
+                .iter()
+                .filter_map(|c| c.namespace.as_deref())
+                .collect(),
+            default_locale,
+        }
+    }
+
+    /// Split an i18next `ns:key` when `ns` is a known namespace
+    fn split_namespace<'k>(&self, key: &'k str) -> (Option<&'k str>, &'k str) {
+        match key.split_once(':') {
+            Some((ns, rest)) if self.namespaces.contains(ns) => (Some(ns), rest),
+            _ => (None, key),
+        }
+    }
+
+    fn has_namespace(&self, locale: &str, namespace: &str) -> bool {
+        self.catalogs
+            .iter()
+            .any(|c| c.locale == locale && c.namespace.as_deref() == Some(namespace))
+    }
+
+    /// Locales that do not define the key, leaving out the ones that lack
+    /// the whole namespace it lives in elsewhere (`missing_namespace`)
+    fn missing_from(&self, namespace: Option<&str>, key: &str) -> Vec<&'c str> {
+        let defining: Vec<&Catalog> = self
+            .catalogs
+            .iter()
+            .filter(|c| c.defines(namespace, key))
+            .collect();
+        let homes: BTreeSet<&str> = defining
+            .iter()
+            .filter_map(|c| c.namespace.as_deref())
+            .chain(namespace)
+            .collect();
+        self.locales
+            .iter()
+            .copied()
+            .filter(|locale| !defining.iter().any(|c| c.locale == *locale))
+            .filter(|locale| {
+                homes.is_empty() || homes.iter().any(|ns| self.has_namespace(locale, ns))
+            })
+            .collect()
+    }
+
+    /// A warning on the default locale's file for each namespace another
+    /// locale lacks
+    fn missing_namespaces(&self) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for catalog in self
+            .catalogs
+            .iter()
+            .filter(|c| c.locale == self.default_locale)
+        {
+            let Some(namespace) = catalog.namespace.as_deref() else {
+                continue;
+            };
+            for locale in &self.locales {
+                if self.has_namespace(locale, namespace) {
+                    continue;
+                }
+                findings.push(Finding {
+                    rule: Some("missing_namespace".to_string()),
+                    ..catalog_finding(
+                        Severity::Warning,
+                        msg!(
+                            "i18n-missing-namespace",
+                            locale = locale,
+                            namespace = namespace,
+                            default = self.default_locale
+                        ),
+                        catalog.file.clone(),
+                        1,
+                        Some(msg!(
+                            "i18n-missing-namespace.suggestion",
+                            locale = locale,
+                            namespace = namespace
+                        )),
+                        None,
+                    )
+                });
+            }
+        }
+        findings
+    }
+
+    /// An info finding per catalog key (plural forms together) that no
+    /// lookup names, at its default-locale definition when there is one
+    fn unused(&self, used: &Used) -> Vec<Finding> {
+        // (namespace, base key) → [(catalog, line)]
+        let mut keys: BTreeMap<KeyId, Vec<(&Catalog, usize)>> = BTreeMap::new();
+        for catalog in self.catalogs {
+            for key in &catalog.keys {
+                keys.entry((catalog.namespace.as_deref(), key.base()))
+                    .or_default()
+                    .push((catalog, key.line));
+            }
+        }
+
+        let mut findings = Vec::new();
+        for ((namespace, key), definitions) in keys {
+            if used.covers(namespace, key) {
+                continue;
+            }
+            let (catalog, line) = definitions
+                .iter()
+                .find(|(c, _)| c.locale == self.default_locale)
+                .unwrap_or(&definitions[0]);
+            let locales: BTreeSet<&str> =
+                definitions.iter().map(|(c, _)| c.locale.as_str()).collect();
+            let locales = locales.into_iter().collect::<Vec<_>>().join(", ");
+            findings.push(Finding {
+                rule: Some("unused_key".to_string()),
+                confidence: Confidence::Medium,
+                ..catalog_finding(
+                    Severity::Info,
+                    msg!("i18n-unused-key", key = key, locales = &locales),
+                    catalog.file.clone(),
+                    *line,
+                    Some(msg!("i18n-unused-key.suggestion", key = key)),
+                    None,
+                )
+            });
+        }
+        findings
+    }
+}
+
+/// Catalog keys the lookups may name
+#[derive(Default)]
+struct Used {
+    /// `(namespace, key)`; `None` matches a key in any namespace
+    keys: HashSet<(Option<String>, String)>,
+    /// Literal prefixes of dynamic keys
+    prefixes: Vec<String>,
+}
+
+impl Used {
+    fn literal(&mut self, namespace: Option<&str>, key: &str, namespaces: &BTreeSet<&str>) {
+        let key = catalog::plural_base(key);
+        self.keys
+            .insert((namespace.map(String::from), key.to_string()));
+        if namespace.is_none() {
+            // `checkout.title` may mean `title` in the `checkout` namespace
+            for ns in namespaces {
+                if let Some(rest) = key.strip_prefix(ns).and_then(|r| r.strip_prefix('.')) {
+                    self.keys.insert((Some(ns.to_string()), rest.to_string()));
+                }
+            }
+        }
+    }
+
+    fn covers(&self, namespace: Option<&str>, key: &str) -> bool {
+        let named = self.keys.contains(&(None, key.to_string()))
+            || namespace
+                .is_some_and(|ns| self.keys.contains(&(Some(ns.to_string()), key.to_string())));
+        named
+            || self.prefixes.iter().filter(|p| !p.is_empty()).any(|p| {
+                key.starts_with(p.as_str())
+                    || namespace.is_some_and(|ns| {
+                        format!("{}.{}", ns, key).starts_with(p.as_str())
+                            || format!("{}:{}", ns, key).starts_with(p.as_str())
+                    })
+            })
+    }
+}
+
+fn missing_key(file: &Path, line: usize, key: &str, locales: &[&str]) -> Finding {
+    let locales = locales.join(", ");
+    Finding {
+        rule: Some("missing_key".to_string()),
+        ..catalog_finding(
+            Severity::Warning,
+            msg!("i18n-missing-key", key = key, locales = &locales),
+            file.to_path_buf(),
+            line,
+            Some(msg!(
+                "i18n-missing-key.suggestion",
+                key = key,
+                locales = locales
+            )),
+            None,
+        )
+    }
+}
+
+fn dynamic_key(file: &Path, line: usize, expr: &str) -> Finding {
+    let expr: String = expr.split_whitespace().collect::<Vec<_>>().join(" ");
+    let expr = match expr.char_indices().nth(MAX_EXPR) {
+        Some((end, _)) => format!("{}…", &expr[..end]),
+        None => expr,
+    };
+    Finding {
+        rule: Some("dynamic_key".to_string()),
+        ..catalog_finding(
+            Severity::Info,
+            msg!("i18n-dynamic-key", expr = expr),
+            file.to_path_buf(),
+            line,
+            Some(msg!("i18n-dynamic-key.suggestion")),
+            None,
+        )
+    }
+}
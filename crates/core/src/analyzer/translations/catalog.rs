@@ -0,0 +1,418 @@
+//! Translation catalogs: locating them and flattening their keys
+//!
+//! A catalog pattern is a repository-relative glob with a `{locale}` and an
+//! optional `{namespace}` placeholder (`**/locales/{locale}/{namespace}.json`).
+//! JSON and YAML catalogs are flattened to dotted key paths; arrays are
+//! leaves, and a document whose only root key is its locale (Rails-style
+//! `en:`) is unwrapped. gettext `.po` files contribute their msgids, except
+//! entries left untranslated. Values are never interpreted, so ICU message
+//! syntax is carried as-is.
+
+use crate::analyzer::kubernetes::yaml::{self, Node, Value};
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Suffixes i18next appends for plural forms (`items_one`, `items_other`)
+const PLURAL_SUFFIXES: &[&str] = &[
+    "_zero", "_one", "_two", "_few", "_many", "_other", "_plural",
+];
+
+/// A `catalogs` entry, compiled
+#[derive(Debug, Clone)]
+pub struct CatalogPattern {
+    regex: Regex,
+}
+
+impl CatalogPattern {
+    /// Compile a pattern; it must hold `{locale}` once and `{namespace}` at
+    /// most once. `**` spans directories, `*` stays within one.
+    pub fn parse(pattern: &str) -> Option<Self> {
+        let pattern = pattern.trim().trim_start_matches("./");
+        if pattern.matches("{locale}").count() != 1 || pattern.matches("{namespace}").count() > 1 {
+            return None;
+        }
+        let mut regex = String::from("^");
+        let mut rest = pattern;
+        while !rest.is_empty() {
+            if let Some(r) = rest.strip_prefix("{locale}") {
+                regex.push_str("(?P<locale>[^/]+)");
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix("{namespace}") {
+                regex.push_str("(?P<namespace>[^/]+)");
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix("**/") {
+                regex.push_str("(?:.*/)?");
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix("**") {
+                regex.push_str(".*");
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix('*') {
+                regex.push_str("[^/]*");
+                rest = r;
+            } else {
+                let c = rest.chars().next()?;
+                if matches!(c, '{' | '}') {
+                    return None;
+                }
+                regex.push_str(&regex::escape(&c.to_string()));
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        regex.push('$');
+        Regex::new(&regex).ok().map(|regex| Self { regex })
+    }
+
+    /// `(locale, namespace)` when the `/`-separated relative path matches
+    pub fn captures(&self, rel: &str) -> Option<(String, Option<String>)> {
+        let caps = self.regex.captures(rel)?;
+        let locale = caps.name("locale")?.as_str();
+        if !is_locale(locale) {
+            return None;
+        }
+        let namespace = caps.name("namespace").map(|m| m.as_str().to_string());
+        Some((locale.to_string(), namespace))
+    }
+}
+
+/// `en`, `pt-BR`, `zh_Hant`, `sr-Latn-RS`
+fn is_locale(name: &str) -> bool {
+    let mut parts = name.split(['-', '_']);
+    let language = parts.next().unwrap_or("");
+    (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_lowercase())
+        && parts.all(|p| (2..=8).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// One flattened key and the line it is written on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogKey {
+    pub key: String,
+    pub line: usize,
+}
+
+impl CatalogKey {
+    /// The key without a plural suffix (`cart.items_one` → `cart.items`)
+    pub fn base(&self) -> &str {
+        plural_base(&self.key)
+    }
+}
+
+/// Strip an i18next plural suffix (`_one`, `_ordinal_few`, ...)
+pub fn plural_base(key: &str) -> &str {
+    PLURAL_SUFFIXES
+        .iter()
+        .find_map(|suffix| key.strip_suffix(suffix))
+        .map(|base| base.strip_suffix("_ordinal").unwrap_or(base))
+        .filter(|base| !base.is_empty() && !base.ends_with('.'))
+        .unwrap_or(key)
+}
+
+/// The keys of one catalog file
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    pub locale: String,
+    pub namespace: Option<String>,
+    pub file: PathBuf,
+    pub keys: Vec<CatalogKey>,
+    /// Every key and plural base, for lookups
+    names: HashSet<String>,
+}
+
+impl Catalog {
+    /// Read a catalog file; `None` when it is not valid JSON, YAML or PO
+    pub fn read(file: PathBuf, locale: String, namespace: Option<String>) -> Option<Self> {
+        let content = std::fs::read_to_string(&file).ok()?;
+        let extension = file.extension()?.to_str()?.to_ascii_lowercase();
+        let keys = match extension.as_str() {
+            "json" => json_keys(&content, &locale)?,
+            "yaml" | "yml" => yaml_keys(&content, &locale),
+            "po" => po_keys(&content),
+            _ => return None,
+        };
+        Some(Self::new(file, locale, namespace, keys))
+    }
+
+    pub fn new(
+        file: PathBuf,
+        locale: String,
+        namespace: Option<String>,
+        keys: Vec<CatalogKey>,
+    ) -> Self {
+        let names = keys
+            .iter()
+            .flat_map(|k| [k.key.clone(), k.base().to_string()])
+            .collect();
+        Self {
+            locale,
+            namespace,
+            file,
+            keys,
+            names,
+        }
+    }
+
+    /// Whether `key` (or a plural form of it) is defined here
+    pub fn has(&self, key: &str) -> bool {
+        self.names.contains(key)
+    }
+
+    /// Whether a lookup of `key`, optionally qualified by `namespace`,
+    /// resolves here. Unqualified keys may also start with this catalog's
+    /// namespace (`checkout.title` in `checkout.json`).
+    pub fn defines(&self, namespace: Option<&str>, key: &str) -> bool {
+        match namespace {
+            Some(ns) => self.namespace.as_deref() == Some(ns) && self.has(key),
+            None => {
+                self.has(key)
+                    || self.namespace.as_deref().is_some_and(|ns| {
+                        key.strip_prefix(ns)
+                            .and_then(|rest| rest.strip_prefix('.'))
+                            .is_some_and(|rest| self.has(rest))
+                    })
+            }
+        }
+    }
+}
+
+// ── JSON ────────────────────────────────────────────────────────────────────
+
+/// Flatten a JSON catalog, keeping the line of every key
+fn json_keys(content: &str, locale: &str) -> Option<Vec<CatalogKey>> {
+    serde_json::from_str::<serde_json::Value>(content).ok()?;
+    let mut keys = Vec::new();
+    let mut reader = JsonReader {
+        bytes: content.as_bytes(),
+        pos: 0,
+        line: 1,
+    };
+    reader.skip_ws();
+    if reader.peek() == Some(b'{') {
+        reader.object(&mut Vec::new(), &mut keys);
+    }
+    Some(unwrap_locale(keys, locale))
+}
+
+/// Tokenizer over a document already known to be valid JSON
+struct JsonReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    line: usize,
+}
+
+impl JsonReader<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) {
+        if self.peek() == Some(b'\n') {
+            self.line += 1;
+        }
+        self.pos += 1;
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn string(&mut self) -> String {
+        self.bump();
+        let start = self.pos;
+        while let Some(b) = self.peek() {
+            match b {
+                b'\\' => {
+                    self.bump();
+                    self.bump();
+                }
+                b'"' => break,
+                _ => self.bump(),
+            }
+        }
+        let raw = &self.bytes[start..self.pos];
+        self.bump();
+        let quoted = format!("\"{}\"", String::from_utf8_lossy(raw));
+        serde_json::from_str(&quoted).unwrap_or_default()
+    }
+
+    fn object(&mut self, path: &mut Vec<String>, keys: &mut Vec<CatalogKey>) {
+        self.bump();
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                Some(b'"') => {}
+                Some(b',') => {
+                    self.bump();
+                    continue;
+                }
+                _ => {
+                    self.bump();
+                    return;
+                }
+            }
+            let line = self.line;
+            let key = self.string();
+            self.skip_ws();
+            self.bump(); // ':'
+            self.skip_ws();
+            path.push(key);
+            if self.peek() == Some(b'{') {
+                self.object(path, keys);
+            } else {
+                keys.push(CatalogKey {
+                    key: path.join("."),
+                    line,
+                });
+                self.skip_value();
+            }
+            path.pop();
+        }
+    }
+
+    /// Skip a scalar or array
+    fn skip_value(&mut self) {
+        let mut depth = 0usize;
+        while let Some(b) = self.peek() {
+            match b {
+                b'"' => {
+                    self.string();
+                    if depth == 0 {
+                        return;
+                    }
+                    continue;
+                }
+                b'[' | b'{' => depth += 1,
+                b']' | b'}' if depth == 0 => return,
+                b']' | b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.bump();
+                        return;
+                    }
+                }
+                b',' if depth == 0 => return,
+                _ => {}
+            }
+            self.bump();
+        }
+    }
+}
+
+// ── YAML ────────────────────────────────────────────────────────────────────
+
+fn yaml_keys(content: &str, locale: &str) -> Vec<CatalogKey> {
+    let mut keys = Vec::new();
+    for document in yaml::parse_documents(content) {
+        flatten_yaml(&document, &mut Vec::new(), &mut keys);
+    }
+    unwrap_locale(keys, locale)
+}
+
+fn flatten_yaml(node: &Node, path: &mut Vec<String>, keys: &mut Vec<CatalogKey>) {
+    for entry in node.entries() {
+        path.push(entry.key.clone());
+        if matches!(entry.value.value, Value::Mapping(_)) {
+            flatten_yaml(&entry.value, path, keys);
+        } else {
+            keys.push(CatalogKey {
+                key: path.join("."),
+                line: entry.line,
+            });
+        }
+        path.pop();
+    }
+}
+
+/// Drop a single root key naming the locale (`en: { checkout: ... }`)
+fn unwrap_locale(keys: Vec<CatalogKey>, locale: &str) -> Vec<CatalogKey> {
+    let prefix = format!("{}.", locale);
+    if keys.is_empty() || !keys.iter().all(|k| k.key.starts_with(&prefix)) {
+        return keys;
+    }
+    keys.into_iter()
+        .map(|k| CatalogKey {
+            key: k.key[prefix.len()..].to_string(),
+            line: k.line,
+        })
+        .collect()
+}
+
+// ── gettext ─────────────────────────────────────────────────────────────────
+
+/// msgids of translated entries; the header, fuzzy and obsolete (`#~`)
+/// entries are skipped
+fn po_keys(content: &str) -> Vec<CatalogKey> {
+    #[derive(PartialEq)]
+    enum Field {
+        Id,
+        Str,
+        Other,
+    }
+
+    let mut keys = Vec::new();
+    let mut entry: Option<(String, usize)> = None;
+    let mut translation = String::new();
+    let mut field = Field::Other;
+    let mut fuzzy = false;
+    let mut finish =
+        |entry: &mut Option<(String, usize)>, translation: &mut String, fuzzy: bool| {
+            if let Some((id, line)) = entry.take() {
+                if !id.is_empty() && !translation.is_empty() && !fuzzy {
+                    keys.push(CatalogKey { key: id, line });
+                }
+            }
+            translation.clear();
+        };
+
+    for (i, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.starts_with("#,") {
+            finish(&mut entry, &mut translation, fuzzy);
+            fuzzy = line.contains("fuzzy");
+            field = Field::Other;
+        } else if let Some(rest) = line.strip_prefix("msgid ") {
+            if entry.is_some() {
+                finish(&mut entry, &mut translation, fuzzy);
+                fuzzy = false;
+            }
+            entry = Some((po_string(rest), i + 1));
+            field = Field::Id;
+        } else if let Some(rest) = line
+            .strip_prefix("msgstr ")
+            .or_else(|| line.strip_prefix("msgstr[0] "))
+        {
+            translation.push_str(&po_string(rest));
+            field = Field::Str;
+        } else if line.starts_with('"') {
+            match field {
+                Field::Id => {
+                    if let Some((id, _)) = entry.as_mut() {
+                        id.push_str(&po_string(line));
+                    }
+                }
+                Field::Str => translation.push_str(&po_string(line)),
+                Field::Other => {}
+            }
+        } else if line.starts_with("msg") {
+            // msgid_plural, msgstr[1..], msgctxt
+            field = Field::Other;
+        }
+    }
+    finish(&mut entry, &mut translation, fuzzy);
+    keys
+}
+
+fn po_string(text: &str) -> String {
+    let inner = text
+        .trim()
+        .strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .unwrap_or("");
+    inner
+        .replace("\\n", "\n")
+        .replace("\\t", "\t")
+        .replace("\\\"", "\"")
+        .replace("\\\\", "\\")
+}
@@ -0,0 +1,312 @@
+//! Translation lookups in source code
+//!
+//! JavaScript/TypeScript and Python sources are parsed with tree-sitter and
+//! every call whose callee is in the lookup table has its first argument
+//! inspected, as has every `i18nKey` attribute of a JSX `<Trans>` element.
+//! A string literal (or a template without substitutions) is a key; anything
+//! else is dynamic, keeping the literal text it starts with
+//! (`` `errors.${code}` `` → `errors.`).
+
+use crate::parser::grammar::{language_for, Family, Grammar};
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Lookup calls and components of one i18n library
+pub struct Framework {
+    pub name: &'static str,
+    pub family: Family,
+    /// Callees whose first argument is a key. A dotted name also matches
+    /// with a receiver in front (`this.translate.instant`)
+    pub functions: &'static [&'static str],
+    /// `(component, attribute)` pairs holding a key in JSX
+    pub components: &'static [(&'static str, &'static str)],
+}
+
+/// Built-in lookup table
+pub const FRAMEWORKS: &[Framework] = &[
+    Framework {
+        name: "i18next",
+        family: Family::Js,
+        functions: &["t", "i18n.t", "i18next.t"],
+        components: &[("Trans", "i18nKey")],
+    },
+    Framework {
+        name: "vue-i18n",
+        family: Family::Js,
+        functions: &["t", "$t", "tc", "$tc", "i18n.global.t"],
+        components: &[],
+    },
+    Framework {
+        name: "angular",
+        family: Family::Js,
+        functions: &[
+            "translate.instant",
+            "translate.get",
+            "translate.stream",
+            "i18n.translate",
+        ],
+        components: &[],
+    },
+    Framework {
+        name: "gettext",
+        family: Family::Python,
+        functions: &[
+            "_",
+            "gettext",
+            "ngettext",
+            "gettext_lazy",
+            "ngettext_lazy",
+            "_l",
+        ],
+        components: &[],
+    },
+];
+
+/// The key a lookup asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key {
+    Literal(String),
+    /// Built at runtime; `prefix` is the literal text it starts with
+    Dynamic {
+        prefix: String,
+        expr: String,
+    },
+}
+
+/// One lookup site
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lookup {
+    pub key: Key,
+    pub line: usize,
+}
+
+/// Callees and components selected by `[analyzers.i18n]`
+#[derive(Debug, Clone, Default)]
+pub struct LookupTable {
+    js: Vec<String>,
+    python: Vec<String>,
+    components: Vec<(String, String)>,
+}
+
+impl LookupTable {
+    /// Table for the named frameworks plus extra callees for every language
+    pub fn new(frameworks: &[String], functions: &[String]) -> Self {
+        let mut table = Self::default();
+        for framework in FRAMEWORKS
+            .iter()
+            .filter(|f| frameworks.iter().any(|n| n == f.name))
+        {
+            let names = match framework.family {
+                Family::Js => &mut table.js,
+                Family::Python => &mut table.python,
+                _ => continue,
+            };
+            names.extend(framework.functions.iter().map(|f| f.to_string()));
+            table.components.extend(
+                framework
+                    .components
+                    .iter()
+                    .map(|(c, a)| (c.to_string(), a.to_string())),
+            );
+        }
+        table.js.extend(functions.iter().cloned());
+        table.python.extend(functions.iter().cloned());
+        table
+    }
+
+    fn matches(&self, family: Family, callee: &str) -> bool {
+        let callee = callee.strip_prefix("this.").unwrap_or(callee);
+        let names = match family {
+            Family::Js => &self.js,
+            Family::Python => &self.python,
+            _ => return false,
+        };
+        names.iter().any(|name| {
+            callee == name
+                || (name.contains('.')
+                    && callee
+                        .strip_suffix(name.as_str())
+                        .is_some_and(|receiver| receiver.ends_with('.')))
+        })
+    }
+}
+
+/// Whether `path` is scanned for lookups
+pub fn supports(path: &Path) -> bool {
+    grammar(path).is_some()
+}
+
+fn grammar(path: &Path) -> Option<Grammar> {
+    language_for(path).filter(|g| matches!(g.family, Family::Python | Family::Js))
+}
+
+/// Every lookup in one source, in source order
+pub fn scan(path: &Path, content: &str, table: &LookupTable) -> Vec<Lookup> {
+    let Some(grammar) = grammar(path) else {
+        return Vec::new();
+    };
+    let Some(tree) = grammar.parse(content) else {
+        return Vec::new();
+    };
+    let mut scanner = Scanner {
+        family: grammar.family,
+        source: content.as_bytes(),
+        table,
+        lookups: Vec::new(),
+    };
+    scanner.walk(tree.root_node());
+    scanner.lookups
+}
+
+struct Scanner<'a> {
+    family: Family,
+    source: &'a [u8],
+    table: &'a LookupTable,
+    lookups: Vec<Lookup>,
+}
+
+impl<'a> Scanner<'a> {
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.source).unwrap_or("")
+    }
+
+    fn walk(&mut self, node: Node) {
+        match node.kind() {
+            "call_expression" | "call" => self.call(node),
+            "jsx_opening_element" | "jsx_self_closing_element" => self.element(node),
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.walk(child);
+        }
+    }
+
+    fn call(&mut self, node: Node) {
+        let (Some(callee), Some(args)) = (
+            node.child_by_field_name("function"),
+            node.child_by_field_name("arguments"),
+        ) else {
+            return;
+        };
+        let callee: String = self
+            .text(callee)
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        if !self.table.matches(self.family, &callee) {
+            return;
+        }
+        let mut cursor = args.walk();
+        let first = args
+            .named_children(&mut cursor)
+            .find(|a| !matches!(a.kind(), "comment" | "keyword_argument"));
+        if let Some(arg) = first {
+            self.push(arg, node);
+        }
+    }
+
+    fn element(&mut self, node: Node) {
+        let Some(name) = node.child_by_field_name("name") else {
+            return;
+        };
+        let name = self.text(name);
+        let mut cursor = node.walk();
+        for attribute in node.named_children(&mut cursor) {
+            if attribute.kind() != "jsx_attribute" {
+                continue;
+            }
+            let Some(attr_name) = attribute.named_child(0) else {
+                continue;
+            };
+            let attr_name = self.text(attr_name);
+            let wanted = self
+                .table
+                .components
+                .iter()
+                .any(|(c, a)| c == name && a == attr_name);
+            if !wanted {
+                continue;
+            }
+            let Some(mut value) = attribute.named_child(1) else {
+                continue;
+            };
+            if value.kind() == "jsx_expression" {
+                match value.named_child(0) {
+                    Some(inner) => value = inner,
+                    None => continue,
+                }
+            }
+            self.push(value, attribute);
+        }
+    }
+
+    fn push(&mut self, arg: Node, site: Node) {
+        let key = self.key(arg);
+        self.lookups.push(Lookup {
+            key,
+            line: site.start_position().row + 1,
+        });
+    }
+
+    fn key(&self, node: Node) -> Key {
+        if let Some(literal) = self.literal(node) {
+            return Key::Literal(literal);
+        }
+        Key::Dynamic {
+            prefix: self.prefix(node),
+            expr: self.text(node).to_string(),
+        }
+    }
+
+    /// The value of a string literal without interpolation
+    fn literal(&self, node: Node) -> Option<String> {
+        match node.kind() {
+            "string" if !has_child(node, "interpolation") => {
+                Some(unquote(self.text(node)).to_string())
+            }
+            "template_string" if !has_child(node, "template_substitution") => {
+                Some(unquote(self.text(node)).to_string())
+            }
+            "parenthesized_expression" => self.literal(node.named_child(0)?),
+            _ => None,
+        }
+    }
+
+    /// Literal text a dynamic key starts with
+    fn prefix(&self, node: Node) -> String {
+        match node.kind() {
+            "binary_expression" | "binary_operator" => node
+                .child_by_field_name("left")
+                .map(|left| self.literal(left).unwrap_or_else(|| self.prefix(left)))
+                .unwrap_or_default(),
+            "template_string" => {
+                let text = self.text(node);
+                let body = text.strip_prefix('`').unwrap_or(text);
+                body.split("${").next().unwrap_or("").to_string()
+            }
+            "string" => {
+                let text = unquote(self.text(node));
+                text.split('{').next().unwrap_or("").to_string()
+            }
+            _ => String::new(),
+        }
+    }
+}
+
+fn has_child(node: Node, kind: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node.named_children(&mut cursor).any(|c| c.kind() == kind);
+    found
+}
+
+/// Strip string prefixes (`f`, `u`, `r`, `b`) and quotes
+fn unquote(text: &str) -> &str {
+    let text = text.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    for quote in ["\"\"\"", "'''", "\"", "'", "`"] {
+        if let Some(inner) = text.strip_prefix(quote).and_then(|t| t.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    text
+}
@@ -2,11 +2,18 @@
 //!
 //! Reports symbols (functions, classes, variables) that are exported from a file but
 //! never imported or called by any other file in the graph.
+//!
+//! Go build-constrained variants of one symbol count as used if any variant
+//! has callers, and are otherwise reported once for the whole group. Python
+//! functions a framework invokes (routes, tasks, commands, fixtures) are
+//! roots and never reported.
 
 use crate::analyzer::GraphAnalyzer;
 use crate::config::RevetConfig;
 use crate::finding::{Finding, Severity};
 use crate::graph::{CodeGraph, EdgeKind, NodeId, NodeKind};
+use crate::parser::go_build::{self, PlatformVariants};
+use crate::parser::python_frameworks;
 use std::path::Path;
 
 /// Names commonly used as entry points — never flagged as unused.
@@ -55,6 +62,22 @@ fn is_top_level(graph: &CodeGraph, node_id: NodeId) -> bool {
     })
 }
 
+/// Returns true if `node_id` is a top-level function, class or variable —
+/// the symbols this analyzer checks
+pub fn is_top_level_symbol(graph: &CodeGraph, node_id: NodeId) -> bool {
+    graph.node(node_id).is_some_and(|node| {
+        matches!(
+            node.kind(),
+            NodeKind::Function | NodeKind::Class | NodeKind::Variable
+        )
+    }) && is_top_level(graph, node_id)
+}
+
+/// Returns true if `node_id` is exported: a public top-level symbol
+pub fn is_exported(graph: &CodeGraph, node_id: NodeId) -> bool {
+    is_top_level_symbol(graph, node_id) && graph.node(node_id).is_some_and(|n| n.is_public())
+}
+
 /// Returns true if any node has a `Calls` or `References` edge pointing to `node_id`.
 fn has_callers(graph: &CodeGraph, node_id: NodeId) -> bool {
     graph
@@ -76,55 +99,96 @@ impl GraphAnalyzer for UnusedExportsAnalyzer {
         config.modules.dead_code
     }
 
-    fn analyze_graph(&self, graph: &CodeGraph, _config: &RevetConfig) -> Vec<Finding> {
-        let mut findings = Vec::new();
-
-        for (node_id, node) in graph.nodes() {
-            let kind = node.kind();
-            if !matches!(
-                kind,
-                NodeKind::Function | NodeKind::Class | NodeKind::Variable
-            ) {
-                continue;
-            }
-
-            if ENTRY_POINT_NAMES.contains(&node.name()) {
-                continue;
-            }
-
-            if is_test_file(node.file_path()) {
-                continue;
-            }
-
-            if !is_top_level(graph, node_id) {
-                continue;
-            }
-
-            if has_callers(graph, node_id) {
-                continue;
-            }
-
-            let (severity, message) = if node.is_public() {
-                (
-                    Severity::Warning,
-                    format!(
-                        "Exported `{}` ({:?}) has no callers or references",
-                        node.name(),
-                        kind
-                    ),
-                )
-            } else {
-                (
-                    Severity::Info,
-                    format!(
-                        "Private `{}` ({:?}) is unreachable — no callers in this codebase",
-                        node.name(),
-                        kind
-                    ),
-                )
-            };
-
-            findings.push(Finding {
+    fn module(&self) -> Option<&'static str> {
+        Some("dead_code")
+    }
+
+    fn analyze_graph(&self, graph: &CodeGraph, config: &RevetConfig) -> Vec<Finding> {
+        find_unused(graph, config, None)
+    }
+
+    fn analyze_file(
+        &self,
+        graph: &CodeGraph,
+        file: &Path,
+        _content: &str,
+        config: &RevetConfig,
+    ) -> Vec<Finding> {
+        find_unused(graph, config, Some(file))
+    }
+}
+
+/// Flag top-level symbols without callers, optionally restricted to one file.
+fn find_unused(graph: &CodeGraph, config: &RevetConfig, only_file: Option<&Path>) -> Vec<Finding> {
+    let platforms = go_build::parse_platforms(&config.parser.go.platforms);
+    let variants = PlatformVariants::build(graph);
+    let mut findings = Vec::new();
+
+    for (node_id, node) in graph.nodes() {
+        if only_file.is_some_and(|f| node.file_path() != f) {
+            continue;
+        }
+
+        let kind = node.kind();
+        if !matches!(
+            kind,
+            NodeKind::Function | NodeKind::Class | NodeKind::Variable
+        ) {
+            continue;
+        }
+
+        if ENTRY_POINT_NAMES.contains(&node.name()) {
+            continue;
+        }
+
+        if is_test_file(node.file_path()) {
+            continue;
+        }
+
+        if python_frameworks::entry_point(node).is_some() {
+            continue;
+        }
+
+        if !go_build::is_active(node, &platforms) {
+            continue;
+        }
+
+        if !is_top_level(graph, node_id) {
+            continue;
+        }
+
+        let used = match variants.group_of(node_id) {
+            Some(group) => group.members.iter().any(|&m| has_callers(graph, m)),
+            None => has_callers(graph, node_id),
+        };
+        if used {
+            continue;
+        }
+
+        let (severity, message) = if node.is_public() {
+            (
+                Severity::Warning,
+                format!(
+                    "Exported `{}` ({:?}) has no callers or references",
+                    node.name(),
+                    kind
+                ),
+            )
+        } else {
+            (
+                Severity::Info,
+                format!(
+                    "Private `{}` ({:?}) is unreachable — no callers in this codebase",
+                    node.name(),
+                    kind
+                ),
+            )
+        };
+
+        findings.push((
+            node_id,
+            "unused",
+            Finding {
                 id: String::new(), // renumbered by dispatcher
                 severity,
                 message,
@@ -136,9 +200,9 @@ impl GraphAnalyzer for UnusedExportsAnalyzer {
                 ),
                 fix_kind: None,
                 ..Default::default()
-            });
-        }
-
-        findings
+            },
+        ));
     }
+
+    variants.dedupe(findings)
 }
@@ -0,0 +1,296 @@
+//! Upgrade advisor (`UPGRADE`) — finds uses of APIs that a runtime upgrade
+//! removes, deprecates or changes
+//!
+//! What each runtime removed and when comes from knowledge packs (see
+//! [`pack`]): Python and Node.js are bundled, `[upgrade] packs` adds or
+//! replaces them. A runtime is checked once it has a target version, from
+//! `[upgrade]` (`python = "3.13"`) or inferred from the nearest
+//! `pyproject.toml` / `package.json` (see [`targets`]).
+//!
+//! Uses are found in the code graph, not by text search: import nodes of
+//! modules outside the repository, and the external-call edges the resolver
+//! links to them. A whole-module entry (`distutils`) is reported at the
+//! import; a named one (`asyncio.get_event_loop`) at each call, or at the
+//! import when the name is imported but never called (`from collections
+//! import Mapping`). Attribute uses the graph does not record — method
+//! aliases such as `TestCase.assertEquals` — are not found.
+//!
+//! - `removed`: the target no longer has the API (error)
+//! - `changed`: the API behaves differently in the target (warning)
+//! - `deprecated`: the API is deprecated in the target — a warning when a
+//!   removal is scheduled, info otherwise
+//! - `invalid_pack`: a configured pack cannot be loaded
+//!
+//! APIs the target has not reached yet are not reported. `revet
+//! upgrade-report` summarizes the same uses per runtime (see [`report`]).
+//!
+//! Finding prefix: `UPGRADE-`
+
+pub mod pack;
+pub mod report;
+pub mod targets;
+
+use crate::analyzer::{catalog_finding, GraphAnalyzer};
+use crate::config::RevetConfig;
+use crate::finding::{Finding, Severity};
+use crate::graph::{CodeGraph, EdgeMetadata, NodeData, NodeId};
+use crate::msg;
+use pack::{PackError, Status};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use targets::Targets;
+
+/// `[modules]` name of the analyzer
+pub const MODULE: &str = "upgrade";
+
+/// Sub-rules, selectable as `upgrade:<rule>`
+pub const RULES: &[&str] = &["removed", "changed", "deprecated", "invalid_pack"];
+
+/// One use of an API that upgrading to the target affects
+#[derive(Debug, Clone, Serialize)]
+pub struct Usage {
+    /// Runtime key (`python`, `node`)
+    pub runtime: String,
+    /// Runtime display name (`Python`, `Node.js`)
+    #[serde(skip)]
+    pub runtime_name: String,
+    /// `module` or `module.name`, as the pack spells it
+    pub api: String,
+    /// Module the API belongs to
+    pub module: String,
+    pub status: Status,
+    /// Version that removed, changed or deprecated the API
+    pub version: String,
+    /// Version the file is being upgraded to
+    pub target: String,
+    /// Scheduled removal of a deprecated API
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+impl Usage {
+    /// Removed APIs break the upgrade; the rest only warn
+    pub fn is_blocker(&self) -> bool {
+        self.status == Status::Removed
+    }
+
+    fn severity(&self) -> Severity {
+        match self.status {
+            Status::Removed => Severity::Error,
+            Status::Changed => Severity::Warning,
+            Status::Deprecated if self.removal.is_some() => Severity::Warning,
+            Status::Deprecated => Severity::Info,
+        }
+    }
+
+    fn finding(&self) -> Finding {
+        let message = match (self.status, &self.removal, &self.note) {
+            (Status::Removed, ..) => msg!(
+                "upgrade-removed",
+                api = &self.api,
+                runtime = &self.runtime_name,
+                version = &self.version,
+                target = &self.target
+            ),
+            (Status::Changed, _, Some(note)) => msg!(
+                "upgrade-changed-note",
+                api = &self.api,
+                runtime = &self.runtime_name,
+                version = &self.version,
+                target = &self.target,
+                note = note
+            ),
+            (Status::Changed, _, None) => msg!(
+                "upgrade-changed",
+                api = &self.api,
+                runtime = &self.runtime_name,
+                version = &self.version,
+                target = &self.target
+            ),
+            (Status::Deprecated, Some(removal), _) => msg!(
+                "upgrade-deprecated-removal",
+                api = &self.api,
+                runtime = &self.runtime_name,
+                version = &self.version,
+                removal = removal,
+                target = &self.target
+            ),
+            (Status::Deprecated, None, _) => msg!(
+                "upgrade-deprecated",
+                api = &self.api,
+                runtime = &self.runtime_name,
+                version = &self.version,
+                target = &self.target
+            ),
+        };
+        let suggestion = match &self.replacement {
+            Some(replacement) => msg!("upgrade-use-instead", replacement = replacement),
+            None => msg!("upgrade-remove-use", api = &self.api),
+        };
+        Finding {
+            rule: Some(self.status.rule().to_string()),
+            ..catalog_finding(
+                self.severity(),
+                message,
+                self.file.clone(),
+                self.line,
+                Some(suggestion),
+                None,
+            )
+        }
+    }
+}
+
+/// Uses of affected APIs in `graph`, sorted by file and line, and the
+/// configured packs that could not be loaded
+pub fn scan(graph: &CodeGraph, config: &RevetConfig) -> (Vec<Usage>, Vec<PackError>) {
+    let root = graph.root_path();
+    let (packs, errors) = pack::load(&config.upgrade, root);
+    let mut targets = Targets::new(root, &config.upgrade.targets, &config.targets);
+
+    // Calls into external modules, by the import they go through
+    let mut calls: HashMap<NodeId, Vec<(&Path, usize, &str)>> = HashMap::new();
+    for (id, node) in graph.nodes() {
+        for (import, edge) in graph.edges_from(id) {
+            if let Some(EdgeMetadata::ExternalCall { line, callee }) = edge.metadata() {
+                calls
+                    .entry(import)
+                    .or_default()
+                    .push((node.file_path(), *line, callee));
+            }
+        }
+    }
+
+    let mut usages = Vec::new();
+    let mut seen = HashSet::new();
+    for (id, node) in graph.nodes() {
+        let NodeData::Import {
+            module,
+            imported_names,
+            resolved_path: None,
+        } = node.data()
+        else {
+            continue;
+        };
+        let file = node.file_path();
+        let Some(pack) = packs.iter().find(|p| p.accepts(file)) else {
+            continue;
+        };
+        let Some(target) = targets.target(pack, file) else {
+            continue;
+        };
+        let module = pack.normalize(module);
+        for api in pack.apis.iter().filter(|api| api.covers_module(module)) {
+            let Some((status, version)) = api.status(&target.version) else {
+                continue;
+            };
+            let sites: Vec<(&Path, usize)> = match &api.name {
+                None => vec![(file.as_path(), node.line())],
+                Some(name) => {
+                    let called: Vec<(&Path, usize)> = calls
+                        .get(&id)
+                        .into_iter()
+                        .flatten()
+                        .filter(|(_, _, callee)| callee == name)
+                        .map(|(file, line, _)| (*file, *line))
+                        .collect();
+                    if called.is_empty() && imported_names.contains(name) {
+                        vec![(file.as_path(), node.line())]
+                    } else {
+                        called
+                    }
+                }
+            };
+            let display = api.display();
+            for (file, line) in sites {
+                if !seen.insert((file.to_path_buf(), line, display.clone())) {
+                    continue;
+                }
+                usages.push(Usage {
+                    runtime: pack.runtime.clone(),
+                    runtime_name: pack.name.clone(),
+                    api: display.clone(),
+                    module: api.module.clone(),
+                    status,
+                    version: version.to_string(),
+                    target: target.version.to_string(),
+                    removal: match status {
+                        Status::Deprecated => api.removed.as_ref().map(|v| v.to_string()),
+                        _ => None,
+                    },
+                    replacement: api.replacement.clone(),
+                    note: api.note.clone(),
+                    file: file.to_path_buf(),
+                    line,
+                });
+            }
+        }
+    }
+    usages.sort_by(|a, b| (&a.file, a.line, &a.api).cmp(&(&b.file, b.line, &b.api)));
+    (usages, errors)
+}
+
+/// Graph analyzer that reports uses of APIs the target runtime affects
+pub struct UpgradeAnalyzer;
+
+impl UpgradeAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for UpgradeAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphAnalyzer for UpgradeAnalyzer {
+    fn name(&self) -> &str {
+        "Upgrade Advisor"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "UPGRADE"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.upgrade
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some(MODULE)
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn analyze_graph(&self, graph: &CodeGraph, config: &RevetConfig) -> Vec<Finding> {
+        let (usages, errors) = scan(graph, config);
+        let invalid = errors.into_iter().map(|e| Finding {
+            rule: Some("invalid_pack".to_string()),
+            ..catalog_finding(
+                Severity::Warning,
+                msg!("upgrade-invalid-pack", error = &e.message),
+                e.file,
+                e.line,
+                Some(msg!("upgrade-invalid-pack.suggestion")),
+                None,
+            )
+        });
+        invalid.chain(usages.iter().map(Usage::finding)).collect()
+    }
+
+    /// Knowledge packs and manifests are read from disk, outside the graph
+    fn memoizable(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,399 @@
+//! Upgrade knowledge packs — which APIs a runtime removed, deprecated or
+//! changed, and in which version
+//!
+//! A pack is a TOML file describing one runtime:
+//!
+//! ```toml
+//! runtime    = "python"
+//! name       = "Python"
+//! extensions = ["py", "pyi"]
+//!
+//! [[api]]
+//! module      = "distutils"
+//! deprecated  = "3.10"
+//! removed     = "3.12"
+//! replacement = "setuptools, or `packaging` for version handling"
+//!
+//! [[api]]
+//! module     = "asyncio"
+//! name       = "get_event_loop"
+//! deprecated = "3.12"
+//! changed    = "3.14"
+//! note       = "raises RuntimeError when no event loop is running"
+//! ```
+//!
+//! An entry without `name` covers the whole module and its submodules.
+//! `module_prefix` is stripped from imported module names before matching
+//! (`node:` for Node.js). `infer` lists the manifest values the target
+//! version is read from when `[upgrade]` declares none: a dotted key into a
+//! TOML or JSON file, holding a version range whose lower bound is taken.
+//! A runtime that is also a [`Language`](crate::language_target::Language)
+//! (`python`, `node`) takes the project's declared language target first.
+//!
+//! Python and Node.js packs are bundled; `[upgrade] packs` replaces them or
+//! adds packs for other runtimes.
+
+use crate::config::UpgradeConfig;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Packs compiled into revet
+const BUNDLED: &[(&str, &str)] = &[
+    ("python.toml", include_str!("python.toml")),
+    ("node.toml", include_str!("node.toml")),
+];
+
+/// A dotted release number; missing components compare as zero, so
+/// `3.12` == `3.12.0`
+#[derive(Debug, Clone, Eq)]
+pub struct Version(Vec<u64>);
+
+impl Version {
+    /// `3.12`, `20`, `v18.17.1`; a trailing `.x` or `.*` is ignored
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().trim_start_matches('v');
+        let text = text
+            .strip_suffix(".x")
+            .or_else(|| text.strip_suffix(".*"))
+            .unwrap_or(text);
+        let parts = text
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect::<Option<Vec<u64>>>()?;
+        Some(Self(parts))
+    }
+
+    /// Lowest version a range admits: `>=3.9,<4` → 3.9, `^18 || ^20` → 18,
+    /// `~=3.10` → 3.10, `>= 16.0.0` → 16.0.0
+    pub fn lower_bound(range: &str) -> Option<Self> {
+        let mut lowest: Option<Self> = None;
+        for alternative in range.split("||") {
+            let mut clauses: Vec<String> = Vec::new();
+            // `>= 16` is one clause, `>=16 <21` two
+            for token in alternative.split([',', ' ']).filter(|t| !t.is_empty()) {
+                match clauses.last_mut() {
+                    Some(last) if last.chars().all(|c| "<>=!~^".contains(c)) => {
+                        last.push_str(token)
+                    }
+                    _ => clauses.push(token.to_string()),
+                }
+            }
+            for clause in clauses {
+                if clause.starts_with('<') || clause.starts_with("!=") {
+                    continue;
+                }
+                let bound = clause.trim_start_matches(['>', '=', '~', '^']);
+                let Some(version) = Self::parse(bound) else {
+                    continue;
+                };
+                if lowest.as_ref().is_none_or(|l| version < *l) {
+                    lowest = Some(version);
+                }
+            }
+        }
+        lowest
+    }
+
+    fn component(&self, i: usize) -> u64 {
+        self.0.get(i).copied().unwrap_or(0)
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (0..self.0.len().max(other.0.len()))
+            .map(|i| self.component(i).cmp(&other.component(i)))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.0.iter().map(u64::to_string).collect();
+        f.write_str(&parts.join("."))
+    }
+}
+
+/// What upgrading to a target does to an API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// Gone: code using it breaks
+    Removed,
+    /// Still there, but behaves differently
+    Changed,
+    /// Still there, with a deprecation warning or a scheduled removal
+    Deprecated,
+}
+
+impl Status {
+    /// Sub-rule id of findings with this status
+    pub fn rule(self) -> &'static str {
+        match self {
+            Status::Removed => "removed",
+            Status::Changed => "changed",
+            Status::Deprecated => "deprecated",
+        }
+    }
+}
+
+/// Why a pack cannot be loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackError {
+    /// The pack file; empty for content parsed without one
+    pub file: PathBuf,
+    /// 1-based line of the problem
+    pub line: usize,
+    pub message: String,
+}
+
+impl PackError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            file: PathBuf::new(),
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.file.display(), self.line, self.message)
+    }
+}
+
+/// Where a target version is read from when `[upgrade]` declares none
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Inference {
+    /// Manifest file name, looked up from a file's directory upwards
+    pub file: String,
+    /// Dotted key of the version range in it
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawPack {
+    runtime: String,
+    #[serde(default)]
+    name: Option<String>,
+    extensions: Vec<String>,
+    #[serde(default)]
+    module_prefix: Option<String>,
+    #[serde(default)]
+    infer: Vec<Inference>,
+    #[serde(default)]
+    api: Vec<RawApi>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawApi {
+    module: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    deprecated: Option<String>,
+    #[serde(default)]
+    changed: Option<String>,
+    #[serde(default)]
+    removed: Option<String>,
+    #[serde(default)]
+    replacement: Option<String>,
+    #[serde(default)]
+    note: Option<String>,
+}
+
+/// One API entry of a pack
+#[derive(Debug, Clone)]
+pub struct Api {
+    /// Module as imported (`distutils`, `crypto`)
+    pub module: String,
+    /// Symbol of the module; `None` for the whole module
+    pub name: Option<String>,
+    pub deprecated: Option<Version>,
+    pub changed: Option<Version>,
+    pub removed: Option<Version>,
+    /// What to use instead
+    pub replacement: Option<String>,
+    /// How the behavior changed
+    pub note: Option<String>,
+}
+
+impl Api {
+    /// `module` or `module.name`
+    pub fn display(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{}.{}", self.module, name),
+            None => self.module.clone(),
+        }
+    }
+
+    /// The strongest status the API has reached at `target`, with the
+    /// version it was reached in
+    pub fn status(&self, target: &Version) -> Option<(Status, &Version)> {
+        [
+            (Status::Removed, &self.removed),
+            (Status::Changed, &self.changed),
+            (Status::Deprecated, &self.deprecated),
+        ]
+        .into_iter()
+        .find_map(|(status, version)| {
+            version
+                .as_ref()
+                .filter(|v| *v <= target)
+                .map(|v| (status, v))
+        })
+    }
+
+    /// Whether `module` (prefix already stripped) is this entry's module,
+    /// or for whole-module entries one of its submodules
+    pub fn covers_module(&self, module: &str) -> bool {
+        module == self.module
+            || (self.name.is_none()
+                && module
+                    .strip_prefix(self.module.as_str())
+                    .is_some_and(|rest| rest.starts_with(['.', '/'])))
+    }
+}
+
+/// A runtime's knowledge pack
+#[derive(Debug, Clone)]
+pub struct Pack {
+    /// Key of the runtime in `[upgrade]` (`python`, `node`)
+    pub runtime: String,
+    /// Display name (`Python`, `Node.js`)
+    pub name: String,
+    /// Extensions of the runtime's source files, without the dot
+    pub extensions: Vec<String>,
+    pub module_prefix: Option<String>,
+    pub infer: Vec<Inference>,
+    pub apis: Vec<Api>,
+}
+
+impl Pack {
+    /// Parse and check a pack file's content
+    pub fn parse(content: &str) -> Result<Self, PackError> {
+        let raw: RawPack = toml::from_str(content).map_err(|e| {
+            let line = e
+                .span()
+                .map_or(1, |span| content[..span.start].matches('\n').count() + 1);
+            PackError::new(line, e.message())
+        })?;
+        if raw.runtime.trim().is_empty() {
+            return Err(PackError::new(1, "`runtime` is empty"));
+        }
+        let mut apis = Vec::with_capacity(raw.api.len());
+        for (i, api) in raw.api.into_iter().enumerate() {
+            let label = format!("api {} ({})", i + 1, api.module);
+            let version = |field: &str, value: Option<String>| match value {
+                None => Ok(None),
+                Some(text) => Version::parse(&text).map(Some).ok_or_else(|| {
+                    PackError::new(
+                        1,
+                        format!("{}: {} = {:?} is not a version", label, field, text),
+                    )
+                }),
+            };
+            let api = Api {
+                deprecated: version("deprecated", api.deprecated)?,
+                changed: version("changed", api.changed)?,
+                removed: version("removed", api.removed)?,
+                module: api.module,
+                name: api.name,
+                replacement: api.replacement,
+                note: api.note,
+            };
+            if api.deprecated.is_none() && api.changed.is_none() && api.removed.is_none() {
+                return Err(PackError::new(
+                    1,
+                    format!(
+                        "{}: needs at least one of deprecated, changed or removed",
+                        label
+                    ),
+                ));
+            }
+            apis.push(api);
+        }
+        Ok(Self {
+            name: raw.name.unwrap_or_else(|| raw.runtime.clone()),
+            runtime: raw.runtime,
+            extensions: raw
+                .extensions
+                .into_iter()
+                .map(|e| e.trim_start_matches('.').to_string())
+                .collect(),
+            module_prefix: raw.module_prefix,
+            infer: raw.infer,
+            apis,
+        })
+    }
+
+    /// Whether `path` is a source file of this runtime
+    pub fn accepts(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| self.extensions.iter().any(|e| e == ext))
+    }
+
+    /// An imported module name as the entries spell it
+    pub fn normalize<'a>(&self, module: &'a str) -> &'a str {
+        self.module_prefix
+            .as_deref()
+            .and_then(|prefix| module.strip_prefix(prefix))
+            .unwrap_or(module)
+    }
+}
+
+/// The bundled packs
+pub fn bundled() -> Vec<Pack> {
+    BUNDLED
+        .iter()
+        .map(|(file, content)| {
+            Pack::parse(content).unwrap_or_else(|e| panic!("bundled pack {}: {}", file, e))
+        })
+        .collect()
+}
+
+/// Runtimes of the bundled packs
+pub fn bundled_runtimes() -> Vec<String> {
+    bundled().into_iter().map(|p| p.runtime).collect()
+}
+
+/// The bundled packs with `[upgrade] packs` applied, and the configured
+/// pack files that could not be loaded
+pub fn load(config: &UpgradeConfig, repo_root: &Path) -> (Vec<Pack>, Vec<PackError>) {
+    let mut packs = bundled();
+    let mut errors = Vec::new();
+    for file in &config.packs {
+        let path = repo_root.join(file);
+        let pack = std::fs::read_to_string(&path)
+            .map_err(|e| PackError::new(1, e.to_string()))
+            .and_then(|content| Pack::parse(&content));
+        match pack {
+            Ok(pack) => match packs.iter_mut().find(|p| p.runtime == pack.runtime) {
+                Some(existing) => *existing = pack,
+                None => packs.push(pack),
+            },
+            Err(e) => errors.push(PackError { file: path, ..e }),
+        }
+    }
+    (packs, errors)
+}
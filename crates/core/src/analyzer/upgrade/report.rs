@@ -0,0 +1,143 @@
+//! `revet upgrade-report` — how ready the repository is for each runtime's
+//! target: blockers (removed APIs) and warnings (changed or deprecated
+//! ones), in total and per module
+//!
+//! Unlike a review, the report ignores `revet-ignore` comments and the
+//! baseline: a silenced use of a removed API still breaks the upgrade.
+
+use super::pack;
+use super::targets::Targets;
+use super::{scan, Usage};
+use crate::config::RevetConfig;
+use crate::discovery::discover_files;
+use crate::parser::ParserDispatcher;
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Readiness of every runtime with a target or an affected use
+#[derive(Debug, Clone, Serialize)]
+pub struct UpgradeReport {
+    pub runtimes: Vec<RuntimeReadiness>,
+}
+
+/// Readiness for one runtime's target
+#[derive(Debug, Clone, Serialize)]
+pub struct RuntimeReadiness {
+    /// Runtime key (`python`, `node`)
+    pub runtime: String,
+    /// Display name (`Python`, `Node.js`)
+    pub name: String,
+    /// Target versions; several when manifests of sub-projects differ
+    pub targets: Vec<String>,
+    /// No blockers
+    pub ready: bool,
+    pub blockers: usize,
+    pub warnings: usize,
+    /// Counts per API module, most blockers first
+    pub modules: Vec<ModuleReadiness>,
+    pub usages: Vec<Usage>,
+}
+
+/// Affected uses of one module's APIs
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleReadiness {
+    pub module: String,
+    pub blockers: usize,
+    pub warnings: usize,
+}
+
+impl UpgradeReport {
+    /// Parse the source files of the packs' runtimes under `repo_root` and
+    /// report on them. A configured pack that cannot be loaded is an error.
+    pub fn from_repo(repo_root: &Path, config: &RevetConfig) -> Result<Self> {
+        let (packs, errors) = pack::load(&config.upgrade, repo_root);
+        if let Some(error) = errors.first() {
+            anyhow::bail!("upgrade pack {}", error);
+        }
+        let dispatcher = ParserDispatcher::new_with_config(config);
+        let files: Vec<_> = discover_files(
+            repo_root,
+            &dispatcher.supported_extensions(),
+            &config.ignore.paths,
+        )?
+        .into_iter()
+        .filter(|f| packs.iter().any(|p| p.accepts(f)))
+        .collect();
+        let (graph, _) = dispatcher.parse_files_parallel(&files, repo_root.to_path_buf());
+        let (usages, _) = scan(&graph, config);
+        let mut report = Self::new(usages);
+
+        // Runtimes targeted without affected uses are ready
+        let mut targets = Targets::new(repo_root, &config.upgrade.targets, &config.targets);
+        for pack in &packs {
+            if report.runtimes.iter().any(|r| r.runtime == pack.runtime) {
+                continue;
+            }
+            if let Some(target) = targets.for_repo(pack) {
+                report.runtimes.push(RuntimeReadiness {
+                    runtime: pack.runtime.clone(),
+                    name: pack.name.clone(),
+                    targets: vec![target.version.to_string()],
+                    ready: true,
+                    blockers: 0,
+                    warnings: 0,
+                    modules: Vec::new(),
+                    usages: Vec::new(),
+                });
+            }
+        }
+        report.runtimes.sort_by(|a, b| a.runtime.cmp(&b.runtime));
+        Ok(report)
+    }
+
+    /// Group `usages` by runtime and module
+    pub fn new(usages: Vec<Usage>) -> Self {
+        let mut by_runtime: BTreeMap<String, Vec<Usage>> = BTreeMap::new();
+        for usage in usages {
+            by_runtime
+                .entry(usage.runtime.clone())
+                .or_default()
+                .push(usage);
+        }
+        let runtimes = by_runtime
+            .into_iter()
+            .map(|(runtime, usages)| {
+                let mut targets: Vec<String> = usages.iter().map(|u| u.target.clone()).collect();
+                targets.sort_by_key(|t| pack::Version::parse(t));
+                targets.dedup();
+                let mut modules: BTreeMap<&str, ModuleReadiness> = BTreeMap::new();
+                for usage in &usages {
+                    let module =
+                        modules
+                            .entry(usage.module.as_str())
+                            .or_insert_with(|| ModuleReadiness {
+                                module: usage.module.clone(),
+                                blockers: 0,
+                                warnings: 0,
+                            });
+                    if usage.is_blocker() {
+                        module.blockers += 1;
+                    } else {
+                        module.warnings += 1;
+                    }
+                }
+                let mut modules: Vec<ModuleReadiness> = modules.into_values().collect();
+                modules.sort_by_key(|m| std::cmp::Reverse(m.blockers));
+                let blockers = usages.iter().filter(|u| u.is_blocker()).count();
+                RuntimeReadiness {
+                    name: usages[0].runtime_name.clone(),
+                    runtime,
+                    targets,
+                    ready: blockers == 0,
+                    blockers,
+                    warnings: usages.len() - blockers,
+                    modules,
+                    usages,
+                }
+            })
+            .collect();
+        Self { runtimes }
+    }
+}
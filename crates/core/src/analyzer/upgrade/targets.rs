@@ -0,0 +1,147 @@
+//! Target versions: declared in `[upgrade]`, else the project's language
+//! target for a runtime revet knows (see [`crate::language_target`]: the
+//! lower bound of `pyproject.toml`'s `requires-python` or `package.json`'s
+//! `engines.node`), else inferred from the manifest values a pack lists
+
+use super::pack::{Pack, Version};
+use crate::language_target::{Language, LanguageTarget, LanguageTargets, TargetSource};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Where a target version came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// `[upgrade] <runtime> = "..."`, `--target` or `[targets]`
+    Config,
+    /// A manifest value, with the manifest's path
+    Manifest(PathBuf),
+}
+
+/// A runtime's target version for one file
+#[derive(Debug, Clone)]
+pub struct Target {
+    pub version: Version,
+    pub source: Source,
+}
+
+/// Resolves each file's target, reading every manifest at most once
+pub struct Targets<'a> {
+    root: PathBuf,
+    configured: &'a BTreeMap<String, String>,
+    languages: LanguageTargets<'a>,
+    inferred: HashMap<(String, PathBuf), Option<Target>>,
+}
+
+impl<'a> Targets<'a> {
+    /// Targets from `[upgrade]` (`configured`), then from the language
+    /// targets `[targets]` (`languages`) and manifests declare
+    pub fn new(
+        root: &Path,
+        configured: &'a BTreeMap<String, String>,
+        languages: &'a BTreeMap<String, String>,
+    ) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            configured,
+            languages: LanguageTargets::new(root, languages),
+            inferred: HashMap::new(),
+        }
+    }
+
+    /// The version `file` is being upgraded to under `pack`'s runtime, if
+    /// any is declared or can be inferred
+    pub fn target(&mut self, pack: &Pack, file: &Path) -> Option<Target> {
+        if let Some(target) = self.declared(pack) {
+            return Some(target);
+        }
+        if let Some(language) = Language::from_key(&pack.runtime) {
+            if let Some(target) = self.languages.declared(language, file) {
+                return from_language(target);
+            }
+        }
+        self.inferred_in(pack, file.parent()?)
+    }
+
+    /// The runtime's target for the repository as a whole: declared, or
+    /// inferred from the manifest at the root
+    pub fn for_repo(&mut self, pack: &Pack) -> Option<Target> {
+        if let Some(target) = self.declared(pack) {
+            return Some(target);
+        }
+        if let Some(language) = Language::from_key(&pack.runtime) {
+            let target = self.languages.for_repo(language);
+            if target.is_declared() {
+                return from_language(target);
+            }
+        }
+        let root = self.root.clone();
+        self.inferred_in(pack, &root)
+    }
+
+    fn declared(&self, pack: &Pack) -> Option<Target> {
+        let declared = self.configured.get(&pack.runtime)?;
+        Version::parse(declared).map(|version| Target {
+            version,
+            source: Source::Config,
+        })
+    }
+
+    /// The first manifest value found from `dir` up to the repository root
+    fn inferred_in(&mut self, pack: &Pack, dir: &Path) -> Option<Target> {
+        let key = (pack.runtime.clone(), dir.to_path_buf());
+        if let Some(cached) = self.inferred.get(&key) {
+            return cached.clone();
+        }
+        let here = pack.infer.iter().find_map(|inference| {
+            let manifest = dir.join(&inference.file);
+            manifest_value(&manifest, &inference.key)
+                .and_then(|range| Version::lower_bound(&range))
+                .map(|version| Target {
+                    version,
+                    source: Source::Manifest(manifest),
+                })
+        });
+        let target = match here {
+            Some(target) => Some(target),
+            None if dir == self.root || !dir.starts_with(&self.root) => None,
+            None => dir
+                .parent()
+                .and_then(|parent| self.inferred_in(pack, parent)),
+        };
+        self.inferred.insert(key, target.clone());
+        target
+    }
+}
+
+/// A declared language target as an upgrade target
+fn from_language(target: LanguageTarget) -> Option<Target> {
+    let source = match target.source {
+        TargetSource::Config => Source::Config,
+        TargetSource::Manifest { file, .. } => Source::Manifest(file),
+        TargetSource::Default { .. } => return None,
+    };
+    Some(Target {
+        version: target.version,
+        source,
+    })
+}
+
+/// The string at dotted `key` in a TOML or JSON manifest
+fn manifest_value(path: &Path, key: &str) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let is_json = path.extension().is_some_and(|e| e == "json");
+    let mut parts = key.split('.');
+    if is_json {
+        let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+        parts
+            .try_fold(&value, |value, part| value.get(part))?
+            .as_str()
+            .map(String::from)
+    } else {
+        let value: toml::Value = toml::from_str(&content).ok()?;
+        parts
+            .try_fold(&value, |value, part| value.get(part))?
+            .as_str()
+            .map(String::from)
+    }
+}
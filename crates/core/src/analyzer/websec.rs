@@ -0,0 +1,452 @@
+//! Web security configuration analyzer — CORS, cookies and security headers
+//!
+//! Sub-rules:
+//!
+//! - `cors_wildcard_credentials`: CORS allowing any origin (`*`, or the
+//!   request's own origin reflected back) while allowing credentials
+//! - `cookie_httponly`, `cookie_secure`, `cookie_samesite`: cookies set
+//!   without the flag, where the framework's default leaves it off, or
+//!   with it turned off explicitly
+//! - `security_headers_disabled`: security-header middleware or settings
+//!   turned off (`helmet({ contentSecurityPolicy: false })`,
+//!   `SECURE_HSTS_SECONDS = 0`, `.headers().disable()`,
+//!   `config.force_ssl = false`)
+//! - `security_headers_missing`: a file creating an Express, Fastify or
+//!   Flask app that applies no header middleware, and a Django
+//!   `MIDDLEWARE` list without `SecurityMiddleware`. Medium confidence: the
+//!   headers may be set elsewhere
+//!
+//! Configuration call sites are found in the syntax tree, per framework:
+//! [`js`] (Express, Fastify), [`python`] (Flask, Django), [`java`]
+//! (Spring, servlets) and [`ruby`] (Rails, rack-cors). Each message starts
+//! with the framework pattern that matched (`Express res.cookie()`,
+//! `Django settings`), and suppressions can name the sub-rule
+//! (`revet-ignore websec:cookie_secure`).
+//!
+//! Only literal arguments are judged. A value computed at runtime — an
+//! environment variable, a settings object, a call — is reported at Low
+//! confidence as one to verify at runtime, without suggested code. Other
+//! findings carry the corrected options as suggested code.
+//!
+//! Each sub-rule's severity comes from `[analyzers.websec.severity]`
+//! (defaults: error, warning, warning, warning, warning, info).
+//!
+//! Finding prefix: `WEBSEC-`
+
+pub mod java;
+pub mod js;
+pub mod python;
+pub mod ruby;
+
+use crate::analyzer::{catalog_finding, Analyzer};
+use crate::config::{RevetConfig, WebsecConfig};
+use crate::finding::{Confidence, Finding, InsertContext, Severity};
+use crate::i18n::Msg;
+use crate::msg;
+use crate::parser::grammar::{language_for, Family, Grammar};
+use crate::suggestion;
+use std::path::{Path, PathBuf};
+use tree_sitter::Node;
+
+/// Module name of the web security configuration analyzer
+pub const MODULE: &str = "websec";
+
+/// Sub-rules of the web security configuration analyzer
+pub const RULES: &[&str] = &[
+    "cors_wildcard_credentials",
+    "cookie_httponly",
+    "cookie_secure",
+    "cookie_samesite",
+    "security_headers_disabled",
+    "security_headers_missing",
+];
+
+/// Origin put into suggested CORS configuration in place of a wildcard
+pub const EXAMPLE_ORIGIN: &str = "https://app.example.com";
+
+/// What a configuration call site gets wrong
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    /// One of [`RULES`]
+    pub rule: &'static str,
+    /// The framework pattern that matched: `Express cors()`
+    pub pattern: &'static str,
+    /// Cookie rules: the cookie's name. CORS: the origin as written.
+    /// Header rules: the header option or setting turned off, or the
+    /// middleware that is missing.
+    pub subject: String,
+    /// The option or setting that fixes it, as the suggestion names it:
+    /// `httpOnly: true`, `SESSION_COOKIE_SECURE = True`
+    pub setting: String,
+    pub line: usize,
+    /// The expression the value comes from, when it is not a literal
+    pub runtime: Option<String>,
+    /// Corrected code for the options
+    pub fix: Option<Fix>,
+}
+
+impl Issue {
+    pub fn new(rule: &'static str, pattern: &'static str, line: usize) -> Self {
+        Self {
+            rule,
+            pattern,
+            subject: String::new(),
+            setting: String::new(),
+            line,
+            runtime: None,
+            fix: None,
+        }
+    }
+
+    pub fn subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = subject.into();
+        self
+    }
+
+    pub fn setting(mut self, setting: impl Into<String>) -> Self {
+        self.setting = setting.into();
+        self
+    }
+
+    /// The value is computed from `expression`: verify at runtime
+    pub fn runtime(mut self, expression: impl Into<String>) -> Self {
+        self.runtime = Some(expression.into());
+        self.fix = None;
+        self
+    }
+
+    pub fn fix(mut self, original: impl Into<String>, replacement: impl Into<String>) -> Self {
+        if self.runtime.is_none() {
+            self.fix = Some(Fix {
+                original: original.into(),
+                replacement: replacement.into(),
+            });
+        }
+        self
+    }
+}
+
+/// Suggested code: `original`, from the start of the finding's line on,
+/// replaced by `replacement`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    pub original: String,
+    pub replacement: String,
+}
+
+/// The grammar of `path`, if it is scanned
+pub fn grammar(path: &Path) -> Option<Grammar> {
+    language_for(path).filter(|g| {
+        matches!(
+            g.family,
+            Family::Js | Family::Python | Family::Java | Family::Ruby
+        )
+    })
+}
+
+/// Issues in `source`, in source order
+pub fn scan(family: Family, root: Node, source: &str) -> Vec<Issue> {
+    let mut issues = match family {
+        Family::Js => js::scan(root, source),
+        Family::Python => python::scan(root, source),
+        Family::Java => java::scan(root, source),
+        Family::Ruby => ruby::scan(root, source),
+        _ => Vec::new(),
+    };
+    issues.sort_by_key(|i| i.line);
+    issues
+}
+
+/// Analyzer that reports CORS, cookie and security-header misconfiguration
+/// in web framework code
+pub struct WebsecAnalyzer {
+    cors_wildcard_credentials: Severity,
+    cookie_httponly: Severity,
+    cookie_secure: Severity,
+    cookie_samesite: Severity,
+    security_headers_disabled: Severity,
+    security_headers_missing: Severity,
+}
+
+impl WebsecAnalyzer {
+    /// Create a web security analyzer with the default severities
+    pub fn new() -> Self {
+        Self::with_config(&WebsecConfig::default())
+    }
+
+    /// Create a web security analyzer honouring `[analyzers.websec]`
+    pub fn with_config(config: &WebsecConfig) -> Self {
+        let severity =
+            |rule: &str, default: Severity| match config.severity.get(rule).map(String::as_str) {
+                Some("error") => Severity::Error,
+                Some("warning") => Severity::Warning,
+                Some("info") => Severity::Info,
+                _ => default,
+            };
+        Self {
+            cors_wildcard_credentials: severity("cors_wildcard_credentials", Severity::Error),
+            cookie_httponly: severity("cookie_httponly", Severity::Warning),
+            cookie_secure: severity("cookie_secure", Severity::Warning),
+            cookie_samesite: severity("cookie_samesite", Severity::Warning),
+            security_headers_disabled: severity("security_headers_disabled", Severity::Warning),
+            security_headers_missing: severity("security_headers_missing", Severity::Info),
+        }
+    }
+
+    fn scan_source(&self, path: &Path, content: &str) -> Vec<Finding> {
+        let Some(grammar) = grammar(path) else {
+            return Vec::new();
+        };
+        let Some(tree) = grammar.parse(content) else {
+            return Vec::new();
+        };
+        scan(grammar.family, tree.root_node(), content)
+            .into_iter()
+            .map(|issue| self.finding(path, issue))
+            .collect()
+    }
+
+    fn finding(&self, path: &Path, issue: Issue) -> Finding {
+        let (severity, confidence) = match issue.rule {
+            "cors_wildcard_credentials" => (self.cors_wildcard_credentials, Confidence::High),
+            "cookie_httponly" => (self.cookie_httponly, Confidence::High),
+            "cookie_secure" => (self.cookie_secure, Confidence::High),
+            "cookie_samesite" => (self.cookie_samesite, Confidence::High),
+            "security_headers_disabled" => (self.security_headers_disabled, Confidence::High),
+            _ => (self.security_headers_missing, Confidence::Medium),
+        };
+        let confidence = if issue.runtime.is_some() {
+            Confidence::Low
+        } else {
+            confidence
+        };
+        Finding {
+            rule: Some(issue.rule.to_string()),
+            confidence,
+            suggestion_code: issue.fix.as_ref().and_then(|fix| {
+                suggestion::code(
+                    path,
+                    fix.replacement.clone(),
+                    InsertContext::Replace {
+                        original: fix.original.clone(),
+                    },
+                )
+            }),
+            ..catalog_finding(
+                severity,
+                message(&issue),
+                path.to_path_buf(),
+                issue.line,
+                Some(advice(&issue)),
+                None,
+            )
+        }
+    }
+}
+
+/// The finding's message; the runtime variant for computed values
+fn message(issue: &Issue) -> Msg {
+    let pattern = issue.pattern;
+    let subject = issue.subject.as_str();
+    match (issue.rule, issue.runtime.as_deref()) {
+        ("cors_wildcard_credentials", None) => msg!(
+            "websec-cors-wildcard-credentials",
+            pattern = pattern,
+            origin = subject
+        ),
+        ("cors_wildcard_credentials", Some(value)) => msg!(
+            "websec-cors-wildcard-credentials.runtime",
+            pattern = pattern,
+            value = value
+        ),
+        ("cookie_httponly", None) => {
+            msg!(
+                "websec-cookie-httponly",
+                pattern = pattern,
+                cookie = subject
+            )
+        }
+        ("cookie_httponly", Some(value)) => msg!(
+            "websec-cookie-httponly.runtime",
+            pattern = pattern,
+            cookie = subject,
+            value = value
+        ),
+        ("cookie_secure", None) => {
+            msg!("websec-cookie-secure", pattern = pattern, cookie = subject)
+        }
+        ("cookie_secure", Some(value)) => msg!(
+            "websec-cookie-secure.runtime",
+            pattern = pattern,
+            cookie = subject,
+            value = value
+        ),
+        ("cookie_samesite", None) => {
+            msg!(
+                "websec-cookie-samesite",
+                pattern = pattern,
+                cookie = subject
+            )
+        }
+        ("cookie_samesite", Some(value)) => msg!(
+            "websec-cookie-samesite.runtime",
+            pattern = pattern,
+            cookie = subject,
+            value = value
+        ),
+        ("security_headers_disabled", None) => msg!(
+            "websec-headers-disabled",
+            pattern = pattern,
+            header = subject
+        ),
+        ("security_headers_disabled", Some(value)) => msg!(
+            "websec-headers-disabled.runtime",
+            pattern = pattern,
+            header = subject,
+            value = value
+        ),
+        _ => msg!(
+            "websec-headers-missing",
+            pattern = pattern,
+            middleware = subject
+        ),
+    }
+}
+
+/// The finding's suggestion
+fn advice(issue: &Issue) -> Msg {
+    let setting = issue.setting.as_str();
+    match issue.rule {
+        "cors_wildcard_credentials" => msg!(
+            "websec-cors-wildcard-credentials.suggestion",
+            setting = setting
+        ),
+        "cookie_httponly" => msg!("websec-cookie-httponly.suggestion", setting = setting),
+        "cookie_secure" => msg!("websec-cookie-secure.suggestion", setting = setting),
+        "cookie_samesite" => msg!("websec-cookie-samesite.suggestion", setting = setting),
+        "security_headers_disabled" if setting.is_empty() => {
+            msg!(
+                "websec-headers-disabled.remove",
+                header = issue.subject.as_str()
+            )
+        }
+        "security_headers_disabled" => {
+            msg!("websec-headers-disabled.suggestion", setting = setting)
+        }
+        _ => msg!("websec-headers-missing.suggestion", setting = setting),
+    }
+}
+
+/// `text`, a bracketed list of items (`{...}`, `(...)`, `[...]`), with
+/// `item` added at the end, following its layout: on the same line, or on
+/// a line of its own with the indentation of the last item
+pub fn append_item(text: &str, item: &str) -> String {
+    let (Some(open), Some(close)) = (text.chars().next(), text.chars().last()) else {
+        return text.to_string();
+    };
+    if text.len() < 2 {
+        return text.to_string();
+    }
+    let inner = &text[open.len_utf8()..text.len() - close.len_utf8()];
+    let items = inner.trim_end().trim_end_matches(',');
+    if items.trim().is_empty() {
+        return match open {
+            '{' => format!("{{ {} }}", item),
+            _ => format!("{}{}{}", open, item, close),
+        };
+    }
+    if inner.contains('\n') {
+        let last = items.lines().last().unwrap_or("");
+        let indent = &last[..last.len() - last.trim_start().len()];
+        let closing = inner.rsplit('\n').next().unwrap_or("");
+        let closing = &closing[..closing.len() - closing.trim_start().len()];
+        return format!(
+            "{}{},\n{}{}\n{}{}",
+            open, items, indent, item, closing, close
+        );
+    }
+    let pad = if inner.ends_with(' ') { " " } else { "" };
+    format!("{}{}, {}{}{}", open, items, item, pad, close)
+}
+
+/// `text` of `node` in `source`
+pub fn text<'a>(node: Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
+
+/// 1-based line `node` starts on
+pub fn line(node: Node) -> usize {
+    node.start_position().row + 1
+}
+
+/// A string literal's content without quotes
+pub fn unquote(literal: &str) -> &str {
+    let quoted = literal.trim_start_matches(['r', 'b', 'f', 'u', 'R', 'B', 'F', 'U']);
+    if quoted.starts_with(['"', '\'', '`']) {
+        quoted.trim_matches(['"', '\'', '`'])
+    } else {
+        literal
+    }
+}
+
+/// Every node under `node`, itself included, in source order
+pub fn descendants(node: Node) -> Vec<Node> {
+    let mut out = Vec::new();
+    let mut stack = vec![node];
+    while let Some(current) = stack.pop() {
+        out.push(current);
+        let mut cursor = current.walk();
+        let children: Vec<Node> = current.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    out
+}
+
+impl Default for WebsecAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer for WebsecAnalyzer {
+    fn name(&self) -> &str {
+        "Web security configuration"
+    }
+
+    fn finding_prefix(&self) -> &str {
+        "WEBSEC"
+    }
+
+    fn is_enabled(&self, config: &RevetConfig) -> bool {
+        config.modules.websec
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some(MODULE)
+    }
+
+    fn rules(&self) -> &'static [&'static str] {
+        RULES
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        grammar(path).is_some()
+    }
+
+    fn per_file(&self) -> bool {
+        true
+    }
+
+    fn analyze_files(&self, files: &[PathBuf], _repo_root: &Path) -> Vec<Finding> {
+        files
+            .iter()
+            .filter(|f| self.accepts(f))
+            .filter_map(|f| Some(self.scan_source(f, &std::fs::read_to_string(f).ok()?)))
+            .flatten()
+            .collect()
+    }
+
+    fn analyze_source(&self, path: &Path, content: &str, _repo_root: &Path) -> Vec<Finding> {
+        self.scan_source(path, content)
+    }
+}
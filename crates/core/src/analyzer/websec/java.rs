@@ -0,0 +1,416 @@
+//! Spring and servlet configuration
+//!
+//! | Code | Pattern | Checked |
+//! |------|---------|---------|
+//! | `config.setAllowedOrigins(...)`, `addAllowedOrigin(...)` | `Spring CorsConfiguration` | `*` with `setAllowCredentials(true)` in the same method |
+//! | `registry.addMapping(...).allowedOrigins(...)` | `Spring CorsRegistry` | `*` with `allowCredentials(true)` in the same method |
+//! | `@CrossOrigin(...)` | `Spring @CrossOrigin` | `origins` (default `*`) with `allowCredentials = "true"` |
+//! | `.headers().disable()`, `.frameOptions(f -> f.disable())`, `.headers(AbstractHttpConfigurer::disable)` | `Spring Security headers` | a header configurer turned off |
+//! | `ResponseCookie.from(...)...build()` | `Spring ResponseCookie` | `httpOnly`, `secure`, `sameSite` (all off by default) |
+//! | `Cookie c = new Cookie(...)` | `Servlet Cookie` | `c.setHttpOnly(true)`, `c.setSecure(true)` in the same method |
+//!
+//! The origin patterns (`allowedOriginPatterns("*")`) count as origins:
+//! unlike `allowedOrigins("*")`, Spring accepts them with credentials.
+
+use super::{append_item, descendants, line, text, unquote, Issue, EXAMPLE_ORIGIN};
+use tree_sitter::Node;
+
+/// Calls setting the allowed origins
+const ORIGIN_CALLS: &[&str] = &[
+    "setAllowedOrigins",
+    "addAllowedOrigin",
+    "setAllowedOriginPatterns",
+    "addAllowedOriginPattern",
+    "allowedOrigins",
+    "allowedOriginPatterns",
+];
+
+/// Calls allowing credentials
+const CREDENTIAL_CALLS: &[&str] = &["setAllowCredentials", "allowCredentials"];
+
+/// Spring Security configurers of response headers
+const HEADER_CONFIGURERS: &[&str] = &[
+    "headers",
+    "frameOptions",
+    "contentTypeOptions",
+    "httpStrictTransportSecurity",
+    "xssProtection",
+    "cacheControl",
+];
+
+/// `ResponseCookie` builder methods: (method, rule, corrected argument)
+const COOKIE_BUILDER: &[(&str, &str, &str)] = &[
+    ("httpOnly", "cookie_httponly", "true"),
+    ("secure", "cookie_secure", "true"),
+    ("sameSite", "cookie_samesite", "\"Lax\""),
+];
+
+/// Servlet `Cookie` setters: (method, rule)
+const COOKIE_SETTERS: &[(&str, &str)] = &[
+    ("setHttpOnly", "cookie_httponly"),
+    ("setSecure", "cookie_secure"),
+];
+
+/// Misconfigured calls and annotations under `root`
+pub fn scan(root: Node, source: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for node in descendants(root) {
+        match node.kind() {
+            "method_declaration" | "constructor_declaration" => {
+                cors_calls(node, source, &mut issues)
+            }
+            "annotation" => cross_origin(node, source, &mut issues),
+            "method_invocation" => {
+                headers(node, source, &mut issues);
+                response_cookie(node, source, &mut issues);
+            }
+            "local_variable_declaration" => servlet_cookie(node, source, &mut issues),
+            _ => {}
+        }
+    }
+    issues
+}
+
+fn named(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).collect()
+}
+
+fn name<'a>(invocation: Node, source: &'a str) -> &'a str {
+    invocation
+        .child_by_field_name("name")
+        .map_or("", |n| text(n, source))
+}
+
+/// `name(arguments)` of an invocation, without the object it is called on
+fn call_text<'a>(invocation: Node, source: &'a str) -> &'a str {
+    match (
+        invocation.child_by_field_name("name"),
+        invocation.child_by_field_name("arguments"),
+    ) {
+        (Some(name), Some(arguments)) => &source[name.start_byte()..arguments.end_byte()],
+        _ => text(invocation, source),
+    }
+}
+
+/// The line of an invocation's name, where chained calls put it
+fn name_line(invocation: Node) -> usize {
+    invocation
+        .child_by_field_name("name")
+        .map_or_else(|| line(invocation), line)
+}
+
+fn literal(kind: &str) -> bool {
+    matches!(
+        kind,
+        "string_literal" | "true" | "false" | "null_literal" | "decimal_integer_literal"
+    )
+}
+
+/// Whether `node` holds a `"*"` string, any string at all
+fn origins(node: Node, source: &str) -> (bool, bool) {
+    let strings: Vec<Node> = descendants(node)
+        .into_iter()
+        .filter(|n| n.kind() == "string_literal")
+        .collect();
+    (
+        strings.iter().any(|s| unquote(text(*s, source)) == "*"),
+        !strings.is_empty(),
+    )
+}
+
+/// `setAllowedOrigins(List.of("*"))` with `setAllowCredentials(true)` in
+/// the same method
+fn cors_calls(method: Node, source: &str, issues: &mut Vec<Issue>) {
+    let invocations: Vec<Node> = descendants(method)
+        .into_iter()
+        .filter(|n| n.kind() == "method_invocation")
+        .collect();
+    // `None` for `true`, the expression for a computed value
+    let credentials: Vec<Option<&str>> = invocations
+        .iter()
+        .filter(|i| CREDENTIAL_CALLS.contains(&name(**i, source)))
+        .filter_map(|i| {
+            let argument = named(i.child_by_field_name("arguments")?)
+                .into_iter()
+                .next()?;
+            match argument.kind() {
+                "true" => Some(None),
+                kind if !literal(kind) => Some(Some(text(argument, source))),
+                _ => None,
+            }
+        })
+        .collect();
+    let Some(credentials_runtime) = credentials
+        .iter()
+        .find(|c| c.is_none())
+        .or(credentials.first())
+        .copied()
+    else {
+        return;
+    };
+    for invocation in invocations
+        .iter()
+        .filter(|i| ORIGIN_CALLS.contains(&name(**i, source)))
+    {
+        let method = name(*invocation, source);
+        let pattern = if method.starts_with("set") || method.starts_with("add") {
+            "Spring CorsConfiguration"
+        } else {
+            "Spring CorsRegistry"
+        };
+        let Some(arguments) = invocation.child_by_field_name("arguments") else {
+            continue;
+        };
+        let original = call_text(*invocation, source);
+        let corrected = original.replacen("\"*\"", &format!("\"{}\"", EXAMPLE_ORIGIN), 1);
+        let issue = Issue::new("cors_wildcard_credentials", pattern, name_line(*invocation))
+            .subject(parenthesized(text(arguments, source)))
+            .setting(if corrected == original {
+                format!("{}(\"{}\")", method, EXAMPLE_ORIGIN)
+            } else {
+                corrected.clone()
+            });
+        match origins(arguments, source) {
+            (true, _) => issues.push(match credentials_runtime {
+                Some(expression) => issue.runtime(expression),
+                None => issue.fix(original, corrected),
+            }),
+            (false, false) if credentials_runtime.is_none() => {
+                issues.push(issue.runtime(parenthesized(text(arguments, source))))
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `@CrossOrigin(allowCredentials = "true")`, which allows every origin
+fn cross_origin(annotation: Node, source: &str, issues: &mut Vec<Issue>) {
+    let is_cross_origin = annotation
+        .child_by_field_name("name")
+        .is_some_and(|n| text(n, source).ends_with("CrossOrigin"));
+    let Some(arguments) = annotation.child_by_field_name("arguments") else {
+        return;
+    };
+    if !is_cross_origin {
+        return;
+    }
+    let pairs: Vec<Node> = named(arguments)
+        .into_iter()
+        .filter(|p| p.kind() == "element_value_pair")
+        .collect();
+    let pair = |keys: &[&str]| {
+        pairs.iter().copied().find(|p| {
+            p.child_by_field_name("key")
+                .is_some_and(|k| keys.contains(&text(k, source)))
+        })
+    };
+    let Some(credentials) =
+        pair(&["allowCredentials"]).and_then(|p| p.child_by_field_name("value"))
+    else {
+        return;
+    };
+    let credentials_runtime = match credentials.kind() {
+        "string_literal" if unquote(text(credentials, source)) == "true" => None,
+        kind if !literal(kind) => Some(text(credentials, source)),
+        _ => return,
+    };
+    let corrected = format!("origins = \"{}\"", EXAMPLE_ORIGIN);
+    let origin = pair(&["origins", "value", "originPatterns"]);
+    let issue = Issue::new(
+        "cors_wildcard_credentials",
+        "Spring @CrossOrigin",
+        line(annotation),
+    )
+    .setting(corrected.clone());
+    let issue = match origin.and_then(|p| p.child_by_field_name("value")) {
+        None => issue.subject("*").fix(
+            text(arguments, source),
+            append_item(text(arguments, source), &corrected),
+        ),
+        Some(value) => match origins(value, source) {
+            (true, _) => issue
+                .subject(text(value, source))
+                .fix(text(origin.unwrap_or(value), source), corrected),
+            (false, false) => issue.runtime(text(value, source)),
+            (false, true) => return,
+        },
+    };
+    issues.push(match credentials_runtime {
+        Some(expression) => issue.runtime(expression),
+        None => issue,
+    });
+}
+
+/// `.headers().disable()`, `.frameOptions(f -> f.disable())`,
+/// `.headers(AbstractHttpConfigurer::disable)`
+fn headers(invocation: Node, source: &str, issues: &mut Vec<Issue>) {
+    let configurer = name(invocation, source);
+    if !HEADER_CONFIGURERS.contains(&configurer) {
+        return;
+    }
+    let chained = invocation
+        .parent()
+        .filter(|p| p.kind() == "method_invocation" && name(*p, source) == "disable")
+        .filter(|p| p.child_by_field_name("object") == Some(invocation));
+    let argument = invocation
+        .child_by_field_name("arguments")
+        .and_then(|a| named(a).into_iter().next());
+    let disabled_by_argument = argument.is_some_and(|a| match a.kind() {
+        "method_reference" => text(a, source).ends_with("::disable"),
+        "lambda_expression" => a
+            .child_by_field_name("body")
+            .is_some_and(|b| b.kind() == "method_invocation" && name(b, source) == "disable"),
+        _ => false,
+    });
+    let at = match (chained, disabled_by_argument) {
+        (Some(disable), _) => name_line(disable),
+        (None, true) => name_line(invocation),
+        (None, false) => return,
+    };
+    issues.push(
+        Issue::new("security_headers_disabled", "Spring Security headers", at).subject(configurer),
+    );
+}
+
+/// `ResponseCookie.from("sid", value)...build()`
+fn response_cookie(build: Node, source: &str, issues: &mut Vec<Issue>) {
+    if name(build, source) != "build" {
+        return;
+    }
+    let mut chain = Vec::new();
+    let mut current = build.child_by_field_name("object");
+    while let Some(node) = current.filter(|n| n.kind() == "method_invocation") {
+        chain.push(node);
+        current = node.child_by_field_name("object");
+    }
+    let Some(from) = chain.last().copied().filter(|f| {
+        name(*f, source).starts_with("from")
+            && f.child_by_field_name("object")
+                .is_some_and(|o| text(o, source).ends_with("ResponseCookie"))
+    }) else {
+        return;
+    };
+    let cookie = from
+        .child_by_field_name("arguments")
+        .and_then(|a| named(a).into_iter().next())
+        .map_or("?", |a| unquote(text(a, source)));
+    for (method, rule, corrected) in COOKIE_BUILDER {
+        let fixed = format!("{}({})", method, corrected);
+        let issue = Issue::new(rule, "Spring ResponseCookie", name_line(build))
+            .subject(cookie)
+            .setting(format!(".{}", fixed));
+        match chain.iter().find(|c| name(**c, source) == *method) {
+            Some(call) => {
+                let Some(argument) = call
+                    .child_by_field_name("arguments")
+                    .and_then(|a| named(a).into_iter().next())
+                else {
+                    continue;
+                };
+                let issue = Issue {
+                    line: name_line(*call),
+                    ..issue
+                };
+                match argument.kind() {
+                    "false" | "null_literal" => {
+                        issues.push(issue.fix(call_text(*call, source), fixed))
+                    }
+                    kind if !literal(kind) => issues.push(issue.runtime(text(argument, source))),
+                    _ => {}
+                }
+            }
+            None => {
+                let original = call_text(build, source);
+                issues.push(issue.fix(original, format!("{}.{}", fixed, original)));
+            }
+        }
+    }
+}
+
+/// `Cookie cookie = new Cookie("sid", value);` without the setters
+fn servlet_cookie(declaration: Node, source: &str, issues: &mut Vec<Issue>) {
+    let Some(declarator) = declaration.child_by_field_name("declarator") else {
+        return;
+    };
+    let (Some(variable), Some(value)) = (
+        declarator.child_by_field_name("name"),
+        declarator.child_by_field_name("value"),
+    ) else {
+        return;
+    };
+    let is_cookie = value.kind() == "object_creation_expression"
+        && value.child_by_field_name("type").is_some_and(|t| {
+            let t = text(t, source);
+            t == "Cookie" || t.ends_with(".Cookie")
+        });
+    if !is_cookie {
+        return;
+    }
+    let variable = text(variable, source);
+    let cookie = value
+        .child_by_field_name("arguments")
+        .and_then(|a| named(a).into_iter().next())
+        .map_or("?", |a| unquote(text(a, source)));
+    let mut scope = declaration;
+    while let Some(parent) = scope.parent() {
+        scope = parent;
+        if matches!(
+            scope.kind(),
+            "method_declaration" | "constructor_declaration" | "lambda_expression"
+        ) {
+            break;
+        }
+    }
+    let calls: Vec<Node> = descendants(scope)
+        .into_iter()
+        .filter(|n| n.kind() == "method_invocation")
+        .filter(|n| {
+            n.child_by_field_name("object")
+                .is_some_and(|o| text(o, source) == variable)
+        })
+        .collect();
+    let statement = text(declaration, source);
+    let line_start = source[..declaration.start_byte()]
+        .rfind('\n')
+        .map_or(0, |i| i + 1);
+    let indent: String = source[line_start..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect();
+    for (setter, rule) in COOKIE_SETTERS {
+        let fixed = format!("{}.{}(true);", variable, setter);
+        let issue = Issue::new(rule, "Servlet Cookie", line(declaration))
+            .subject(cookie)
+            .setting(fixed.clone());
+        match calls.iter().find(|c| name(**c, source) == *setter) {
+            Some(call) => {
+                let Some(argument) = call
+                    .child_by_field_name("arguments")
+                    .and_then(|a| named(a).into_iter().next())
+                else {
+                    continue;
+                };
+                let issue = Issue {
+                    line: name_line(*call),
+                    ..issue
+                };
+                match argument.kind() {
+                    "false" => issues
+                        .push(issue.fix(call_text(*call, source), format!("{}(true)", setter))),
+                    kind if !literal(kind) => issues.push(issue.runtime(text(argument, source))),
+                    _ => {}
+                }
+            }
+            None => {
+                issues.push(issue.fix(statement, format!("{}\n{}{}", statement, indent, fixed)))
+            }
+        }
+    }
+}
+
+/// The text between an argument list's parentheses
+fn parenthesized(arguments: &str) -> &str {
+    let inner = arguments.strip_prefix('(').unwrap_or(arguments);
+    inner.strip_suffix(')').unwrap_or(inner)
+}
@@ -0,0 +1,371 @@
+//! Express and Fastify configuration
+//!
+//! | Call | Pattern | Checked |
+//! |------|---------|---------|
+//! | `cors({...})`, `fastify.register(cors, {...})` | `Express cors()`, `Fastify @fastify/cors` | `origin` (default `*`) with `credentials: true` |
+//! | `res.cookie(name, value, {...})` | `Express res.cookie()` | `httpOnly`, `secure`, `sameSite` (all off by default) |
+//! | `reply.setCookie(name, value, {...})` | `Fastify reply.setCookie()` | the same |
+//! | `session({ cookie: {...} })` | `express-session` | `httpOnly: false`; `secure` and `sameSite` (off by default) |
+//! | `cookieSession({...})` | `cookie-session` | the same |
+//! | `helmet({...})`, `fastify.register(helmet, {...})` | `helmet()`, `Fastify @fastify/helmet` | options set to `false` |
+//! | `express()`, `fastify()` | `Express app`, `Fastify app` | no `helmet` anywhere in the file |
+//!
+//! An `origin` of `true` reflects the request's origin, so it counts as a
+//! wildcard; functions deciding per origin are trusted. Options passed as a
+//! variable, spread from one, or set from an expression are computed at
+//! runtime.
+
+use super::{append_item, descendants, line, text, unquote, Issue, EXAMPLE_ORIGIN};
+use tree_sitter::Node;
+
+/// How a cookie API treats an option left out
+#[derive(Clone, Copy)]
+struct Defaults {
+    http_only: bool,
+}
+
+/// The cookie attributes checked: (option, rule, corrected value)
+const COOKIE_OPTIONS: &[(&str, &str, &str)] = &[
+    ("httpOnly", "cookie_httponly", "true"),
+    ("secure", "cookie_secure", "true"),
+    ("sameSite", "cookie_samesite", "\"lax\""),
+];
+
+/// Misconfigured calls under `root`
+pub fn scan(root: Node, source: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut app: Option<(&'static str, &'static str, usize)> = None;
+    for node in descendants(root) {
+        if node.kind() != "call_expression" {
+            continue;
+        }
+        let Some(function) = node.child_by_field_name("function") else {
+            continue;
+        };
+        let Some(arguments) = node.child_by_field_name("arguments") else {
+            continue;
+        };
+        let args = named(arguments);
+        match function.kind() {
+            "identifier" => match text(function, source) {
+                "cors" => {
+                    if let Some(options) = args.first().filter(|a| a.kind() == "object") {
+                        cors(*options, "Express cors()", source, &mut issues);
+                    }
+                }
+                "helmet" => {
+                    if let Some(options) = args.first() {
+                        helmet(*options, "helmet()", source, &mut issues);
+                    }
+                }
+                "session" => {
+                    if let Some(options) = args.first().filter(|a| a.kind() == "object") {
+                        session(node, *options, source, &mut issues);
+                    }
+                }
+                "cookieSession" => {
+                    if let Some(options) = args.first().filter(|a| a.kind() == "object") {
+                        cookie(
+                            node,
+                            Some(*options),
+                            "cookie-session",
+                            "session",
+                            Defaults { http_only: true },
+                            source,
+                            &mut issues,
+                        );
+                    }
+                }
+                "express" if args.is_empty() => {
+                    app.get_or_insert(("Express app", "app.use(helmet())", line(node)));
+                }
+                "fastify" | "Fastify" => {
+                    app.get_or_insert(("Fastify app", "fastify.register(helmet)", line(node)));
+                }
+                _ => {}
+            },
+            "member_expression" => {
+                let method = function
+                    .child_by_field_name("property")
+                    .map_or("", |p| text(p, source));
+                match method {
+                    "register" if args.len() >= 2 => {
+                        let plugin = text(args[0], source);
+                        if plugin.contains("cors") && args[1].kind() == "object" {
+                            cors(args[1], "Fastify @fastify/cors", source, &mut issues);
+                        } else if plugin.contains("helmet") {
+                            helmet(args[1], "Fastify @fastify/helmet", source, &mut issues);
+                        }
+                    }
+                    "cookie" | "setCookie" if args.len() >= 2 => {
+                        let pattern = if method == "cookie" {
+                            "Express res.cookie()"
+                        } else {
+                            "Fastify reply.setCookie()"
+                        };
+                        let name = cookie_name(args[0], source);
+                        cookie(
+                            node,
+                            args.get(2).copied(),
+                            pattern,
+                            &name,
+                            Defaults { http_only: false },
+                            source,
+                            &mut issues,
+                        );
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some((pattern, setting, line)) = app {
+        if !source.contains("helmet") {
+            issues.push(
+                Issue::new("security_headers_missing", pattern, line)
+                    .subject("helmet")
+                    .setting(setting),
+            );
+        }
+    }
+    issues
+}
+
+fn named(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).collect()
+}
+
+/// The `key: value` pair of `object` for `key`
+fn pair<'t>(object: Node<'t>, key: &str, source: &str) -> Option<Node<'t>> {
+    named(object).into_iter().find(|p| {
+        p.kind() == "pair"
+            && p.child_by_field_name("key")
+                .is_some_and(|k| unquote(text(k, source)) == key)
+    })
+}
+
+/// The first spread (`...defaults`) in `object`, which may set any option
+fn spread<'a>(object: Node, source: &'a str) -> Option<&'a str> {
+    named(object)
+        .into_iter()
+        .find(|p| p.kind() == "spread_element")
+        .map(|p| text(p, source))
+}
+
+/// Whether a value kind is a literal rather than computed
+fn literal(kind: &str) -> bool {
+    matches!(
+        kind,
+        "true"
+            | "false"
+            | "string"
+            | "number"
+            | "null"
+            | "undefined"
+            | "array"
+            | "object"
+            | "regex"
+    ) || kind.contains("function")
+}
+
+fn cookie_name(node: Node, source: &str) -> String {
+    unquote(text(node, source)).to_string()
+}
+
+/// `cors({ origin, credentials })`
+fn cors(options: Node, pattern: &'static str, source: &str, issues: &mut Vec<Issue>) {
+    let credentials =
+        pair(options, "credentials", source).and_then(|p| p.child_by_field_name("value"));
+    let credentials_runtime = match credentials.map(|v| v.kind()) {
+        Some("true") => None,
+        Some(kind) if !literal(kind) => credentials.map(|v| text(v, source)),
+        _ => return,
+    };
+    let origin = pair(options, "origin", source);
+    let value = origin.and_then(|p| p.child_by_field_name("value"));
+    let wildcard = match value {
+        None => true,
+        Some(v) => match v.kind() {
+            "true" => true,
+            "string" => unquote(text(v, source)) == "*",
+            "array" => named(v)
+                .iter()
+                .any(|item| item.kind() == "string" && unquote(text(*item, source)) == "*"),
+            kind if literal(kind) => false,
+            _ => {
+                issues.push(
+                    Issue::new("cors_wildcard_credentials", pattern, line(v))
+                        .setting(format!("origin: [\"{}\"]", EXAMPLE_ORIGIN))
+                        .runtime(text(v, source)),
+                );
+                return;
+            }
+        },
+    };
+    if !wildcard {
+        return;
+    }
+    let corrected = format!("origin: [\"{}\"]", EXAMPLE_ORIGIN);
+    let issue = Issue::new(
+        "cors_wildcard_credentials",
+        pattern,
+        line(origin.unwrap_or(options)),
+    )
+    .subject(value.map_or("*", |v| text(v, source)))
+    .setting(corrected.clone());
+    let issue = match (credentials_runtime, origin) {
+        (Some(expression), _) => issue.runtime(expression),
+        (None, Some(origin)) => issue.fix(text(origin, source), corrected),
+        (None, None) => issue.fix(
+            text(options, source),
+            append_item(text(options, source), &corrected),
+        ),
+    };
+    issues.push(issue);
+}
+
+/// `helmet({ contentSecurityPolicy: false })`
+fn helmet(options: Node, pattern: &'static str, source: &str, issues: &mut Vec<Issue>) {
+    if options.kind() != "object" {
+        return;
+    }
+    for entry in named(options).into_iter().filter(|p| p.kind() == "pair") {
+        let (Some(key), Some(value)) = (
+            entry.child_by_field_name("key"),
+            entry.child_by_field_name("value"),
+        ) else {
+            continue;
+        };
+        let key = unquote(text(key, source));
+        let issue = Issue::new("security_headers_disabled", pattern, line(entry)).subject(key);
+        match value.kind() {
+            "false" => {
+                let corrected = format!("{}: true", key);
+                issues.push(
+                    issue
+                        .setting(corrected.clone())
+                        .fix(text(entry, source), corrected),
+                );
+            }
+            kind if !literal(kind) => {
+                issues.push(
+                    issue
+                        .setting(format!("{}: true", key))
+                        .runtime(text(value, source)),
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `session({ cookie: {...} })`: the options live under `cookie`, and
+/// express-session's own defaults leave only `httpOnly` on
+fn session(call: Node, options: Node, source: &str, issues: &mut Vec<Issue>) {
+    let name = pair(options, "name", source)
+        .and_then(|p| p.child_by_field_name("value"))
+        .filter(|v| v.kind() == "string")
+        .map_or_else(|| "connect.sid".to_string(), |v| cookie_name(v, source));
+    let defaults = Defaults { http_only: true };
+    match pair(options, "cookie", source).and_then(|p| p.child_by_field_name("value")) {
+        Some(cookie_options) => cookie(
+            call,
+            Some(cookie_options),
+            "express-session",
+            &name,
+            defaults,
+            source,
+            issues,
+        ),
+        None => {
+            if let Some(expression) = spread(options, source) {
+                for (option, rule, _) in &COOKIE_OPTIONS[1..] {
+                    issues.push(
+                        Issue::new(rule, "express-session", line(call))
+                            .subject(name.as_str())
+                            .setting(format!("cookie.{}", option))
+                            .runtime(expression),
+                    );
+                }
+                return;
+            }
+            // Without a `cookie` object, add one holding the options left off
+            let corrected = "cookie: { secure: true, sameSite: \"lax\" }";
+            for (option, rule, value) in &COOKIE_OPTIONS[1..] {
+                issues.push(
+                    Issue::new(rule, "express-session", line(call))
+                        .subject(name.as_str())
+                        .setting(format!("cookie: {{ {}: {} }}", option, value))
+                        .fix(
+                            text(options, source),
+                            append_item(text(options, source), corrected),
+                        ),
+                );
+            }
+        }
+    }
+}
+
+/// The options of a cookie call: each attribute left off where `defaults`
+/// leave it off, or turned off
+fn cookie(
+    call: Node,
+    options: Option<Node>,
+    pattern: &'static str,
+    name: &str,
+    defaults: Defaults,
+    source: &str,
+    issues: &mut Vec<Issue>,
+) {
+    for (option, rule, value) in COOKIE_OPTIONS {
+        let corrected = format!("{}: {}", option, value);
+        let issue = Issue::new(rule, pattern, line(call))
+            .subject(name)
+            .setting(corrected.clone());
+        let Some(options) = options else {
+            // No options at all: pass them
+            if *option == "httpOnly" && defaults.http_only {
+                continue;
+            }
+            let Some(arguments) = call.child_by_field_name("arguments") else {
+                continue;
+            };
+            let added = format!("{{ {} }}", corrected);
+            issues.push(issue.fix(
+                text(arguments, source),
+                append_item(text(arguments, source), &added),
+            ));
+            continue;
+        };
+        if options.kind() != "object" {
+            issues.push(issue.runtime(text(options, source)));
+            continue;
+        }
+        match pair(options, option, source) {
+            Some(entry) => {
+                let Some(current) = entry.child_by_field_name("value") else {
+                    continue;
+                };
+                let issue = Issue {
+                    line: line(entry),
+                    ..issue
+                };
+                match current.kind() {
+                    "false" => issues.push(issue.fix(text(entry, source), corrected)),
+                    kind if !literal(kind) => issues.push(issue.runtime(text(current, source))),
+                    _ => {}
+                }
+            }
+            None if *option == "httpOnly" && defaults.http_only => {}
+            None => match spread(options, source) {
+                Some(expression) => issues.push(issue.runtime(expression)),
+                None => issues.push(issue.fix(
+                    text(options, source),
+                    append_item(text(options, source), &corrected),
+                )),
+            },
+        }
+    }
+}
@@ -0,0 +1,556 @@
+//! Flask and Django configuration
+//!
+//! | Code | Pattern | Checked |
+//! |------|---------|---------|
+//! | `CORS(app, ...)`, `@cross_origin(...)` | `Flask-CORS`, `Flask-CORS @cross_origin()` | `origins` (default `*`) with `supports_credentials=True` |
+//! | `CORS_ALLOW_ALL_ORIGINS = True` | `django-cors-headers` | with `CORS_ALLOW_CREDENTIALS = True` |
+//! | `response.set_cookie(...)` | `Flask/Django set_cookie()` | `httponly`, `secure`, `samesite` (all off by default) |
+//! | `SESSION_COOKIE_*`, `CSRF_COOKIE_*`, `SECURE_*` | `Django settings` | set to turn the protection off |
+//! | `app.config["..."] = ...`, `app.config.update(...)` | `Flask config` | the same settings |
+//! | `Talisman(app, ...)` | `Flask-Talisman` | options set to `False` or `None` |
+//! | `Flask(__name__)` | `Flask app` | no `Talisman` anywhere in the file |
+//! | `MIDDLEWARE = [...]` | `Django MIDDLEWARE` | no `SecurityMiddleware` |
+//!
+//! Django settings are module-level assignments. Arguments passed with
+//! `**options` may set any keyword, so keywords left out of such a call are
+//! computed at runtime.
+
+use super::{append_item, descendants, line, text, unquote, Issue, EXAMPLE_ORIGIN};
+use tree_sitter::Node;
+
+/// Settings that turn a protection off: (name, rule, values turning it
+/// off, corrected value, cookie or header named in the message)
+const SETTINGS: &[(&str, &str, &[&str], &str, &str)] = &[
+    (
+        "SESSION_COOKIE_SECURE",
+        "cookie_secure",
+        &["False"],
+        "True",
+        "session",
+    ),
+    (
+        "CSRF_COOKIE_SECURE",
+        "cookie_secure",
+        &["False"],
+        "True",
+        "csrftoken",
+    ),
+    (
+        "SESSION_COOKIE_HTTPONLY",
+        "cookie_httponly",
+        &["False"],
+        "True",
+        "session",
+    ),
+    (
+        "SESSION_COOKIE_SAMESITE",
+        "cookie_samesite",
+        &["None", "False"],
+        "\"Lax\"",
+        "session",
+    ),
+    (
+        "CSRF_COOKIE_SAMESITE",
+        "cookie_samesite",
+        &["None", "False"],
+        "\"Lax\"",
+        "csrftoken",
+    ),
+    (
+        "SECURE_SSL_REDIRECT",
+        "security_headers_disabled",
+        &["False"],
+        "True",
+        "SECURE_SSL_REDIRECT",
+    ),
+    (
+        "SECURE_HSTS_SECONDS",
+        "security_headers_disabled",
+        &["0"],
+        "31536000",
+        "SECURE_HSTS_SECONDS",
+    ),
+    (
+        "SECURE_CONTENT_TYPE_NOSNIFF",
+        "security_headers_disabled",
+        &["False"],
+        "True",
+        "SECURE_CONTENT_TYPE_NOSNIFF",
+    ),
+    (
+        "SECURE_REFERRER_POLICY",
+        "security_headers_disabled",
+        &["None"],
+        "\"same-origin\"",
+        "SECURE_REFERRER_POLICY",
+    ),
+    (
+        "SECURE_CROSS_ORIGIN_OPENER_POLICY",
+        "security_headers_disabled",
+        &["None"],
+        "\"same-origin\"",
+        "SECURE_CROSS_ORIGIN_OPENER_POLICY",
+    ),
+];
+
+/// Flask-Talisman options, as [`SETTINGS`]
+const TALISMAN: &[(&str, &str, &[&str], &str, &str)] = &[
+    (
+        "force_https",
+        "security_headers_disabled",
+        &["False"],
+        "True",
+        "force_https",
+    ),
+    (
+        "strict_transport_security",
+        "security_headers_disabled",
+        &["False"],
+        "True",
+        "strict_transport_security",
+    ),
+    (
+        "content_security_policy",
+        "security_headers_disabled",
+        &["None", "False"],
+        "{\"default-src\": \"'self'\"}",
+        "content_security_policy",
+    ),
+    (
+        "frame_options",
+        "security_headers_disabled",
+        &["None", "False"],
+        "\"SAMEORIGIN\"",
+        "frame_options",
+    ),
+    (
+        "session_cookie_secure",
+        "cookie_secure",
+        &["False"],
+        "True",
+        "session",
+    ),
+    (
+        "session_cookie_http_only",
+        "cookie_httponly",
+        &["False"],
+        "True",
+        "session",
+    ),
+];
+
+/// `set_cookie` keywords: (keyword, rule, values turning it off, corrected
+/// value); a keyword left out is off
+const COOKIE_KEYWORDS: &[(&str, &str, &[&str], &str)] = &[
+    ("httponly", "cookie_httponly", &["False"], "True"),
+    ("secure", "cookie_secure", &["False"], "True"),
+    ("samesite", "cookie_samesite", &["None", "False"], "\"Lax\""),
+];
+
+const SECURITY_MIDDLEWARE: &str = "django.middleware.security.SecurityMiddleware";
+
+/// A setting assigned in the file
+struct Setting<'t> {
+    name: String,
+    value: Node<'t>,
+    /// The assignment or keyword argument
+    whole: Node<'t>,
+    /// `NAME = ` or `app.config["NAME"] = `, or `NAME=` for keywords
+    prefix: String,
+    pattern: &'static str,
+}
+
+/// Misconfigured calls and settings under `root`
+pub fn scan(root: Node, source: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut settings = Vec::new();
+    let mut app = None;
+    for node in descendants(root) {
+        match node.kind() {
+            "call" => call(node, source, &mut settings, &mut app, &mut issues),
+            "assignment" => {
+                if let Some(setting) = assignment(node, source) {
+                    settings.push(setting);
+                }
+            }
+            _ => {}
+        }
+    }
+    for setting in &settings {
+        check_setting(setting, source, &mut issues);
+    }
+    django_cors(&settings, source, &mut issues);
+    if let Some(middleware) = settings
+        .iter()
+        .find(|s| s.pattern == "Django settings" && s.name == "MIDDLEWARE")
+    {
+        middleware_list(middleware, source, &mut issues);
+    }
+    if let Some(line) = app {
+        if !source.contains("Talisman") {
+            issues.push(
+                Issue::new("security_headers_missing", "Flask app", line)
+                    .subject("Flask-Talisman")
+                    .setting("Talisman(app)"),
+            );
+        }
+    }
+    issues
+}
+
+fn named(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).collect()
+}
+
+/// Whether a value kind is a literal rather than computed
+fn literal(kind: &str) -> bool {
+    matches!(
+        kind,
+        "true"
+            | "false"
+            | "none"
+            | "integer"
+            | "float"
+            | "string"
+            | "concatenated_string"
+            | "list"
+            | "tuple"
+            | "dictionary"
+            | "set"
+    )
+}
+
+fn call<'t>(
+    node: Node<'t>,
+    source: &str,
+    settings: &mut Vec<Setting<'t>>,
+    app: &mut Option<usize>,
+    issues: &mut Vec<Issue>,
+) {
+    let (Some(function), Some(arguments)) = (
+        node.child_by_field_name("function"),
+        node.child_by_field_name("arguments"),
+    ) else {
+        return;
+    };
+    let callee = text(function, source);
+    let method = callee.rsplit('.').next().unwrap_or(callee);
+    match method {
+        "CORS" => flask_cors(arguments, "Flask-CORS", source, issues),
+        "cross_origin" => flask_cors(arguments, "Flask-CORS @cross_origin()", source, issues),
+        "set_cookie" if function.kind() == "attribute" => {
+            set_cookie(node, arguments, source, issues)
+        }
+        "Talisman" => talisman(arguments, source, issues),
+        "Flask" => {
+            app.get_or_insert(line(node));
+        }
+        "update" | "from_mapping"
+            if callee.ends_with("config.update") || callee.ends_with("config.from_mapping") =>
+        {
+            for argument in named(arguments) {
+                if argument.kind() != "keyword_argument" {
+                    continue;
+                }
+                let (Some(name), Some(value)) = (
+                    argument.child_by_field_name("name"),
+                    argument.child_by_field_name("value"),
+                ) else {
+                    continue;
+                };
+                let name = text(name, source).to_string();
+                settings.push(Setting {
+                    prefix: format!("{}=", name),
+                    name,
+                    value,
+                    whole: argument,
+                    pattern: "Flask config",
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `NAME = value` at module level, or `app.config["NAME"] = value`
+fn assignment<'t>(node: Node<'t>, source: &str) -> Option<Setting<'t>> {
+    let left = node.child_by_field_name("left")?;
+    let value = node.child_by_field_name("right")?;
+    let (name, pattern) = match left.kind() {
+        "identifier" => {
+            let module_level = node
+                .parent()
+                .filter(|p| p.kind() == "expression_statement")
+                .and_then(|p| p.parent())
+                .is_some_and(|p| p.kind() == "module");
+            let name = text(left, source);
+            if !module_level || name.to_uppercase() != name {
+                return None;
+            }
+            (name.to_string(), "Django settings")
+        }
+        "subscript" => {
+            let object = left.child_by_field_name("value")?;
+            let key = left.child_by_field_name("subscript")?;
+            if !text(object, source).ends_with("config") || key.kind() != "string" {
+                return None;
+            }
+            (unquote(text(key, source)).to_string(), "Flask config")
+        }
+        _ => return None,
+    };
+    Some(Setting {
+        prefix: format!("{} = ", text(left, source)),
+        name,
+        value,
+        whole: node,
+        pattern,
+    })
+}
+
+/// A setting from [`SETTINGS`] that turns its protection off
+fn check_setting(setting: &Setting, source: &str, issues: &mut Vec<Issue>) {
+    let Some((_, rule, off, corrected, subject)) =
+        SETTINGS.iter().find(|(name, ..)| *name == setting.name)
+    else {
+        return;
+    };
+    let value = text(setting.value, source);
+    let fixed = format!("{}{}", setting.prefix, corrected);
+    let issue = Issue::new(rule, setting.pattern, line(setting.whole))
+        .subject(*subject)
+        .setting(fixed.clone());
+    if !literal(setting.value.kind()) {
+        issues.push(issue.runtime(value));
+    } else if off.contains(&value) {
+        issues.push(issue.fix(text(setting.whole, source), fixed));
+    }
+}
+
+/// `CORS_ALLOW_ALL_ORIGINS = True` with `CORS_ALLOW_CREDENTIALS = True`
+fn django_cors(settings: &[Setting], source: &str, issues: &mut Vec<Issue>) {
+    let find = |names: &[&str]| {
+        settings
+            .iter()
+            .filter(|s| s.pattern == "Django settings")
+            .find(|s| names.contains(&s.name.as_str()))
+    };
+    let (Some(all), Some(credentials)) = (
+        find(&["CORS_ALLOW_ALL_ORIGINS", "CORS_ORIGIN_ALLOW_ALL"]),
+        find(&["CORS_ALLOW_CREDENTIALS"]),
+    ) else {
+        return;
+    };
+    let state = |setting: &Setting| match setting.value.kind() {
+        "true" => Some(None),
+        kind if !literal(kind) => Some(Some(text(setting.value, source))),
+        _ => None,
+    };
+    let (Some(all_runtime), Some(credentials_runtime)) = (state(all), state(credentials)) else {
+        return;
+    };
+    let corrected = format!("CORS_ALLOWED_ORIGINS = [\"{}\"]", EXAMPLE_ORIGIN);
+    let issue = Issue::new(
+        "cors_wildcard_credentials",
+        "django-cors-headers",
+        line(all.whole),
+    )
+    .subject(text(all.whole, source))
+    .setting(corrected.clone());
+    issues.push(match all_runtime.or(credentials_runtime) {
+        Some(expression) => issue.runtime(expression),
+        None => issue.fix(text(all.whole, source), corrected),
+    });
+}
+
+/// `CORS(app, supports_credentials=True)` without origins, or with `*`
+fn flask_cors(arguments: Node, pattern: &'static str, source: &str, issues: &mut Vec<Issue>) {
+    let keyword = |name: &str| {
+        named(arguments).into_iter().find(|a| {
+            a.kind() == "keyword_argument"
+                && a.child_by_field_name("name")
+                    .is_some_and(|n| text(n, source) == name)
+        })
+    };
+    let Some(credentials) = keyword("supports_credentials") else {
+        return;
+    };
+    let Some(credentials_value) = credentials.child_by_field_name("value") else {
+        return;
+    };
+    let credentials_runtime = match credentials_value.kind() {
+        "true" => None,
+        kind if !literal(kind) => Some(text(credentials_value, source)),
+        _ => return,
+    };
+    let corrected = format!("origins=[\"{}\"]", EXAMPLE_ORIGIN);
+    let origins = keyword("origins");
+    let value = origins.and_then(|o| o.child_by_field_name("value"));
+    let wildcard = match value {
+        None if keyword("resources").is_some() => return,
+        None => true,
+        Some(v) => match v.kind() {
+            "string" => unquote(text(v, source)) == "*",
+            "list" | "tuple" => named(v)
+                .iter()
+                .any(|item| item.kind() == "string" && unquote(text(*item, source)) == "*"),
+            kind if literal(kind) => false,
+            _ => {
+                issues.push(
+                    Issue::new("cors_wildcard_credentials", pattern, line(v))
+                        .setting(corrected)
+                        .runtime(text(v, source)),
+                );
+                return;
+            }
+        },
+    };
+    if !wildcard {
+        return;
+    }
+    let anchor = origins.unwrap_or(credentials);
+    let issue = Issue::new("cors_wildcard_credentials", pattern, line(anchor))
+        .subject(value.map_or("*", |v| text(v, source)))
+        .setting(corrected.clone());
+    issues.push(match (credentials_runtime, origins) {
+        (Some(expression), _) => issue.runtime(expression),
+        (None, Some(origins)) => issue.fix(text(origins, source), corrected),
+        (None, None) => issue.fix(
+            text(credentials, source),
+            format!("{}, {}", corrected, text(credentials, source)),
+        ),
+    });
+}
+
+/// `response.set_cookie("sid", value, ...)`
+fn set_cookie(call: Node, arguments: Node, source: &str, issues: &mut Vec<Issue>) {
+    let args = named(arguments);
+    let name = args
+        .iter()
+        .find(|a| {
+            a.kind() == "string"
+                || (a.kind() == "keyword_argument"
+                    && a.child_by_field_name("name")
+                        .is_some_and(|n| text(n, source) == "key"))
+        })
+        .map(|a| match a.child_by_field_name("value") {
+            Some(value) => unquote(text(value, source)),
+            None => unquote(text(*a, source)),
+        })
+        .unwrap_or("?");
+    let splat = args
+        .iter()
+        .find(|a| a.kind() == "dictionary_splat")
+        .map(|a| text(*a, source));
+    for (keyword, rule, off, corrected) in COOKIE_KEYWORDS {
+        let fixed = format!("{}={}", keyword, corrected);
+        let issue = Issue::new(rule, "Flask/Django set_cookie()", line(call))
+            .subject(name)
+            .setting(fixed.clone());
+        let argument = args.iter().find(|a| {
+            a.kind() == "keyword_argument"
+                && a.child_by_field_name("name")
+                    .is_some_and(|n| text(n, source) == *keyword)
+        });
+        match argument {
+            Some(argument) => {
+                let Some(value) = argument.child_by_field_name("value") else {
+                    continue;
+                };
+                let issue = Issue {
+                    line: line(*argument),
+                    ..issue
+                };
+                if !literal(value.kind()) {
+                    issues.push(issue.runtime(text(value, source)));
+                } else if off.contains(&text(value, source)) {
+                    issues.push(issue.fix(text(*argument, source), fixed));
+                }
+            }
+            None => match splat {
+                Some(expression) => issues.push(issue.runtime(expression)),
+                None => issues.push(issue.fix(
+                    text(arguments, source),
+                    append_item(text(arguments, source), &fixed),
+                )),
+            },
+        }
+    }
+}
+
+/// `Talisman(app, force_https=False, ...)`
+fn talisman(arguments: Node, source: &str, issues: &mut Vec<Issue>) {
+    for argument in named(arguments) {
+        if argument.kind() != "keyword_argument" {
+            continue;
+        }
+        let (Some(name), Some(value)) = (
+            argument.child_by_field_name("name"),
+            argument.child_by_field_name("value"),
+        ) else {
+            continue;
+        };
+        let name = text(name, source);
+        let Some((_, rule, off, corrected, subject)) = TALISMAN.iter().find(|(n, ..)| *n == name)
+        else {
+            continue;
+        };
+        let fixed = format!("{}={}", name, corrected);
+        let issue = Issue::new(rule, "Flask-Talisman", line(argument))
+            .subject(*subject)
+            .setting(fixed.clone());
+        if !literal(value.kind()) {
+            issues.push(issue.runtime(text(value, source)));
+        } else if off.contains(&text(value, source)) {
+            issues.push(issue.fix(text(argument, source), fixed));
+        }
+    }
+}
+
+/// `MIDDLEWARE = [...]` without `SecurityMiddleware`
+fn middleware_list(setting: &Setting, source: &str, issues: &mut Vec<Issue>) {
+    if !matches!(setting.value.kind(), "list" | "tuple") {
+        return;
+    }
+    let items = named(setting.value);
+    if items.iter().any(|item| item.kind() != "string")
+        || items
+            .iter()
+            .any(|item| unquote(text(*item, source)).ends_with("SecurityMiddleware"))
+    {
+        return;
+    }
+    let list = text(setting.value, source);
+    let item = format!("\"{}\"", SECURITY_MIDDLEWARE);
+    issues.push(
+        Issue::new(
+            "security_headers_missing",
+            "Django MIDDLEWARE",
+            line(setting.whole),
+        )
+        .subject("SecurityMiddleware")
+        .setting(item.clone())
+        .fix(list, prepend_item(list, &item)),
+    );
+}
+
+/// `list` with `item` first, following its layout
+fn prepend_item(list: &str, item: &str) -> String {
+    let (open, inner, close) = (
+        &list[..1],
+        &list[1..list.len() - 1],
+        &list[list.len() - 1..],
+    );
+    if inner.trim().is_empty() {
+        return format!("{}{}{}", open, item, close);
+    }
+    match inner.strip_prefix('\n') {
+        Some(rest) => {
+            let first = rest.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+            let indent = &first[..first.len() - first.trim_start().len()];
+            format!("{}\n{}{},\n{}{}", open, indent, item, rest, close)
+        }
+        None => format!("{}{}, {}{}", open, item, inner.trim_start(), close),
+    }
+}
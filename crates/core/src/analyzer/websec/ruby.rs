@@ -0,0 +1,285 @@
+//! Rails and rack-cors configuration
+//!
+//! | Code | Pattern | Checked |
+//! |------|---------|---------|
+//! | `allow do origins ...; resource ..., credentials: true end` | `rack-cors` | `origins '*'` with credentials |
+//! | `cookies[:name] = ...` (also `.signed`, `.encrypted`, `.permanent`) | `Rails cookies` | `httponly`, `secure` (off by default); `same_site: nil` |
+//! | `config.session_store :cookie_store, ...` | `Rails session_store` | `secure`, `httponly`, `same_site` turned off |
+//! | `config.force_ssl = false` | `Rails config` | HTTPS redirects, HSTS and secure cookies off |
+//! | `config.action_dispatch.default_headers = {}`, `.clear` | `Rails config` | every default security header removed |
+//!
+//! Rails sends `SameSite=Lax` unless told otherwise, so only an explicit
+//! `same_site: nil` is reported.
+
+use super::{append_item, descendants, line, text, unquote, Issue, EXAMPLE_ORIGIN};
+use tree_sitter::Node;
+
+/// Options of a cookie hash: (key, rule, values turning it off, corrected
+/// value, whether leaving it out turns it off)
+const COOKIE_KEYS: &[(&str, &str, &[&str], &str, bool)] = &[
+    (
+        "httponly",
+        "cookie_httponly",
+        &["false", "nil"],
+        "true",
+        true,
+    ),
+    ("secure", "cookie_secure", &["false", "nil"], "true", true),
+    (
+        "same_site",
+        "cookie_samesite",
+        &["false", "nil"],
+        ":lax",
+        false,
+    ),
+];
+
+/// Misconfigured assignments and calls under `root`
+pub fn scan(root: Node, source: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for node in descendants(root) {
+        match node.kind() {
+            "assignment" => assignment(node, source, &mut issues),
+            "call" => match method(node, source) {
+                "session_store" => session_store(node, source, &mut issues),
+                "allow" => rack_cors(node, source, &mut issues),
+                "clear"
+                    if node
+                        .child_by_field_name("receiver")
+                        .is_some_and(|r| text(r, source).ends_with("default_headers")) =>
+                {
+                    issues.push(
+                        Issue::new("security_headers_disabled", "Rails config", line(node))
+                            .subject("default_headers"),
+                    );
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    issues
+}
+
+fn named(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).collect()
+}
+
+fn method<'a>(call: Node, source: &'a str) -> &'a str {
+    call.child_by_field_name("method")
+        .map_or("", |m| text(m, source))
+}
+
+fn literal(kind: &str) -> bool {
+    matches!(
+        kind,
+        "true"
+            | "false"
+            | "nil"
+            | "string"
+            | "simple_symbol"
+            | "delimited_symbol"
+            | "integer"
+            | "hash"
+            | "array"
+            | "regex"
+    )
+}
+
+/// The key of a `key: value` or `:key => value` pair
+fn key<'a>(pair: Node, source: &'a str) -> &'a str {
+    pair.child_by_field_name("key")
+        .map_or("", |k| unquote(text(k, source).trim_matches(':')))
+}
+
+fn pairs(node: Node) -> Vec<Node> {
+    named(node)
+        .into_iter()
+        .filter(|p| p.kind() == "pair")
+        .collect()
+}
+
+fn assignment(node: Node, source: &str, issues: &mut Vec<Issue>) {
+    let (Some(left), Some(right)) = (
+        node.child_by_field_name("left"),
+        node.child_by_field_name("right"),
+    ) else {
+        return;
+    };
+    let target = text(left, source);
+    if target.ends_with(".force_ssl") {
+        let fixed = format!("{} = true", target);
+        let issue = Issue::new("security_headers_disabled", "Rails config", line(node))
+            .subject("force_ssl")
+            .setting(fixed.clone());
+        match right.kind() {
+            "false" | "nil" => issues.push(issue.fix(text(node, source), fixed)),
+            kind if !literal(kind) => issues.push(issue.runtime(text(right, source))),
+            _ => {}
+        }
+    } else if target.ends_with(".default_headers") {
+        if right.kind() == "hash" && pairs(right).is_empty() {
+            issues.push(
+                Issue::new("security_headers_disabled", "Rails config", line(node))
+                    .subject("default_headers"),
+            );
+        }
+    } else if left.kind() == "element_reference" && target.starts_with("cookies") {
+        cookie(node, left, right, source, issues);
+    }
+}
+
+/// `cookies[:name] = value` or `cookies[:name] = { value: ..., ... }`
+fn cookie(node: Node, left: Node, right: Node, source: &str, issues: &mut Vec<Issue>) {
+    let name = left
+        .child_by_field_name("object")
+        .map(|object| {
+            let rest = &source[object.end_byte()..left.end_byte()];
+            rest.trim_start_matches('[').trim_end_matches(']').trim()
+        })
+        .map_or("?", |n| unquote(n.trim_start_matches(':')));
+    for (option, rule, off, corrected, default_off) in COOKIE_KEYS {
+        let fixed = format!("{}: {}", option, corrected);
+        let issue = Issue::new(rule, "Rails cookies", line(node))
+            .subject(name)
+            .setting(fixed.clone());
+        if right.kind() != "hash" {
+            if !default_off {
+                continue;
+            }
+            if literal(right.kind()) {
+                let value = text(right, source);
+                issues.push(issue.fix(
+                    text(node, source),
+                    format!("{} = {{ value: {}, {} }}", text(left, source), value, fixed),
+                ));
+            } else {
+                issues.push(issue.runtime(text(right, source)));
+            }
+            continue;
+        }
+        match pairs(right)
+            .into_iter()
+            .find(|p| key(*p, source) == *option)
+        {
+            Some(pair) => {
+                let Some(value) = pair.child_by_field_name("value") else {
+                    continue;
+                };
+                let issue = Issue {
+                    line: line(pair),
+                    ..issue
+                };
+                if off.contains(&text(value, source)) {
+                    issues.push(issue.fix(text(pair, source), fixed));
+                } else if !literal(value.kind()) {
+                    issues.push(issue.runtime(text(value, source)));
+                }
+            }
+            None if !default_off => {}
+            None => match named(right)
+                .into_iter()
+                .find(|p| p.kind() == "hash_splat_argument")
+            {
+                Some(splat) => issues.push(issue.runtime(text(splat, source))),
+                None => issues.push(issue.fix(
+                    text(right, source),
+                    append_item(text(right, source), &fixed),
+                )),
+            },
+        }
+    }
+}
+
+/// `config.session_store :cookie_store, secure: false`
+fn session_store(call: Node, source: &str, issues: &mut Vec<Issue>) {
+    let Some(arguments) = call.child_by_field_name("arguments") else {
+        return;
+    };
+    let mut options = pairs(arguments);
+    for hash in named(arguments).into_iter().filter(|a| a.kind() == "hash") {
+        options.extend(pairs(hash));
+    }
+    for pair in options {
+        let Some((option, rule, off, corrected, _)) =
+            COOKIE_KEYS.iter().find(|(k, ..)| *k == key(pair, source))
+        else {
+            continue;
+        };
+        let Some(value) = pair.child_by_field_name("value") else {
+            continue;
+        };
+        let fixed = format!("{}: {}", option, corrected);
+        let issue = Issue::new(rule, "Rails session_store", line(pair))
+            .subject("session")
+            .setting(fixed.clone());
+        if off.contains(&text(value, source)) {
+            issues.push(issue.fix(text(pair, source), fixed));
+        } else if !literal(value.kind()) {
+            issues.push(issue.runtime(text(value, source)));
+        }
+    }
+}
+
+/// `allow do origins '*'; resource '*', credentials: true end`
+fn rack_cors(allow: Node, source: &str, issues: &mut Vec<Issue>) {
+    let Some(block) = allow.child_by_field_name("block") else {
+        return;
+    };
+    let calls: Vec<Node> = descendants(block)
+        .into_iter()
+        .filter(|n| n.kind() == "call")
+        .collect();
+    let credentials: Vec<Option<&str>> = calls
+        .iter()
+        .filter(|c| method(**c, source) == "resource")
+        .filter_map(|c| {
+            let arguments = c.child_by_field_name("arguments")?;
+            let mut options = pairs(arguments);
+            for hash in named(arguments).into_iter().filter(|a| a.kind() == "hash") {
+                options.extend(pairs(hash));
+            }
+            let value = options
+                .into_iter()
+                .find(|p| key(*p, source) == "credentials")?
+                .child_by_field_name("value")?;
+            match value.kind() {
+                "true" => Some(None),
+                kind if !literal(kind) => Some(Some(text(value, source))),
+                _ => None,
+            }
+        })
+        .collect();
+    let Some(credentials_runtime) = credentials
+        .iter()
+        .find(|c| c.is_none())
+        .or(credentials.first())
+        .copied()
+    else {
+        return;
+    };
+    for origins in calls.iter().filter(|c| method(**c, source) == "origins") {
+        let Some(arguments) = origins.child_by_field_name("arguments") else {
+            continue;
+        };
+        let values = named(arguments);
+        let corrected = format!("origins \"{}\"", EXAMPLE_ORIGIN);
+        let issue = Issue::new("cors_wildcard_credentials", "rack-cors", line(*origins))
+            .subject(text(arguments, source))
+            .setting(corrected.clone());
+        if values
+            .iter()
+            .any(|v| v.kind() == "string" && unquote(text(*v, source)) == "*")
+        {
+            issues.push(match credentials_runtime {
+                Some(expression) => issue.runtime(expression),
+                None => issue.fix(text(*origins, source), corrected),
+            });
+        } else if credentials_runtime.is_none() {
+            if let Some(computed) = values.iter().find(|v| !literal(v.kind())) {
+                issues.push(issue.runtime(text(*computed, source)));
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! Line authorship from git blame, for ownership analysis
+//!
+//! Each file is blamed once at HEAD: every line goes to the author (by
+//! email) of the commit that last changed it, and the newest of those
+//! commits dates the file's last change. Blaming is the expensive part, so
+//! the caller bounds the files, and the result is cached in
+//! `authorship.json` of the state directory keyed on the HEAD commit: a
+//! later run on the same commit only blames files the cache lacks.
+//!
+//! The same pass collects the authors of the non-merge commits inside a
+//! window ending at the HEAD commit's time — the active committers when no
+//! roster is configured. In a shallow clone the walk stops at the shallow
+//! boundary and [`Authorship::shallow`] is set.
+//!
+//! In a partial clone, blame credits every line to the newest commit whose
+//! contents it can read, so a file is only blamed once every version of it
+//! is present: the missing ones are fetched under `[git]`, and files still
+//! missing some are left out and counted as [`Feature::Attribution`].
+
+use anyhow::{Context, Result};
+use git2::{BlameOptions, DiffOptions, Oid, Repository, Sort};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::churn::Window;
+use crate::git_objects::{Feature, GitObjects};
+use crate::storage::{StoragePaths, AUTHORSHIP_FILE};
+
+const DAY_SECS: i64 = 24 * 3600;
+
+/// Who last changed the lines of one file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileAuthors {
+    /// Lines per author email
+    pub lines: BTreeMap<String, usize>,
+    /// Author time of the newest commit in the blame (seconds since the
+    /// epoch)
+    pub last_change: i64,
+}
+
+impl FileAuthors {
+    /// Lines in the file
+    pub fn total(&self) -> usize {
+        self.lines.values().sum()
+    }
+}
+
+/// Blame results and active committers at one HEAD commit
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Authorship {
+    /// HEAD commit the blame was taken at
+    pub head: String,
+    /// Commit time of HEAD (seconds since the epoch)
+    pub head_time: i64,
+    /// The repository is a shallow clone, so older history may be missing
+    pub shallow: bool,
+    /// Window length in days the active authors come from
+    pub window_days: u32,
+    /// Emails of the authors of commits inside the window
+    pub active: BTreeSet<String>,
+    /// Blamed files, keyed by path relative to the repository root
+    /// (`/`-separated)
+    pub files: BTreeMap<String, FileAuthors>,
+}
+
+impl Authorship {
+    /// Load the cached authorship for the current HEAD and blame the `files`
+    /// (relative to `repo_root`) it does not have yet. Files that are not
+    /// committed are left out, and so are files whose history `objects`
+    /// cannot provide. Caching is best-effort.
+    pub fn load_or_compute(
+        repo_root: &Path,
+        paths: &StoragePaths,
+        files: &[String],
+        window: Window,
+        objects: &GitObjects,
+    ) -> Result<Self> {
+        let repo = Repository::discover(repo_root).context("Not a git repository")?;
+        let head = repo.head()?.peel_to_commit()?;
+        let head_id = head.id().to_string();
+
+        let cached = std::fs::read(paths.read_path(AUTHORSHIP_FILE))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Authorship>(&bytes).ok())
+            .filter(|cached| cached.head == head_id);
+        let mut authorship = match cached {
+            Some(cached) => cached,
+            None => Self {
+                head: head_id,
+                head_time: head.time().seconds(),
+                shallow: repo.is_shallow(),
+                ..Default::default()
+            },
+        };
+        let mut changed = false;
+        if authorship.window_days != window.days {
+            authorship.active = active_authors(&repo, authorship.head_time, window)?;
+            authorship.window_days = window.days;
+            changed = true;
+        }
+
+        // Blame paths are relative to the work tree, which may sit above
+        // `repo_root`
+        let prefix = repo
+            .workdir()
+            .and_then(|workdir| {
+                let workdir = workdir.canonicalize().ok()?;
+                let root = repo_root.canonicalize().ok()?;
+                root.strip_prefix(&workdir).ok().map(Path::to_path_buf)
+            })
+            .unwrap_or_default();
+        let pending: Vec<PathBuf> = files
+            .iter()
+            .filter(|file| !authorship.files.contains_key(*file))
+            .map(|file| prefix.join(file))
+            .collect();
+        let incomplete = if objects.is_partial() && !pending.is_empty() {
+            incomplete_history(&repo, head.id(), &pending, objects)?
+        } else {
+            HashSet::new()
+        };
+        objects.skip(Feature::Attribution, incomplete.len());
+        for file in files {
+            if authorship.files.contains_key(file) || incomplete.contains(&prefix.join(file)) {
+                continue;
+            }
+            if let Some(authors) = blame(&repo, head.id(), &prefix.join(file)) {
+                authorship.files.insert(file.clone(), authors);
+                changed = true;
+            }
+        }
+
+        if changed {
+            let cache_path = paths.write_path(AUTHORSHIP_FILE);
+            if let Some(parent) = cache_path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            if let Ok(json) = serde_json::to_vec(&authorship) {
+                let _ = std::fs::write(&cache_path, json);
+            }
+        }
+        Ok(authorship)
+    }
+}
+
+/// Blame `path` (relative to the work tree) at `head`; `None` when it is
+/// not in that commit
+fn blame(repo: &Repository, head: git2::Oid, path: &Path) -> Option<FileAuthors> {
+    let mut options = BlameOptions::new();
+    options.newest_commit(head);
+    let blame = repo.blame_file(path, Some(&mut options)).ok()?;
+    let mut authors = FileAuthors::default();
+    for hunk in blame.iter() {
+        let signature = hunk.final_signature();
+        *authors.lines.entry(identity(&signature)).or_default() += hunk.lines_in_hunk();
+        authors.last_change = authors.last_change.max(signature.when().seconds());
+    }
+    Some(authors)
+}
+
+/// The `paths` (relative to the work tree) with a version `objects` cannot
+/// provide in the history of `head`. Every version of a file is the new
+/// side of the diff of the commit that made it, and tree diffs read no
+/// file contents.
+fn incomplete_history(
+    repo: &Repository,
+    head: Oid,
+    paths: &[PathBuf],
+    objects: &GitObjects,
+) -> Result<HashSet<PathBuf>> {
+    let wanted: HashSet<&Path> = paths.iter().map(PathBuf::as_path).collect();
+    let mut opts = DiffOptions::new();
+    for path in paths {
+        opts.pathspec(path);
+    }
+    opts.disable_pathspec_match(true);
+
+    let mut versions: HashMap<Oid, Vec<PathBuf>> = HashMap::new();
+    let mut walk = repo.revwalk()?;
+    walk.push(head)?;
+    for oid in walk {
+        let Ok(commit) = oid.and_then(|oid| repo.find_commit(oid)) else {
+            break; // shallow boundary
+        };
+        let parent = commit.parent(0).ok().map(|p| p.tree()).transpose()?;
+        let diff =
+            repo.diff_tree_to_tree(parent.as_ref(), Some(&commit.tree()?), Some(&mut opts))?;
+        for delta in diff.deltas() {
+            let file = delta.new_file();
+            if let Some(path) = file.path().filter(|p| wanted.contains(p)) {
+                if !file.id().is_zero() {
+                    versions
+                        .entry(file.id())
+                        .or_default()
+                        .push(path.to_path_buf());
+                }
+            }
+        }
+    }
+
+    let missing = objects.ensure(repo, versions.keys().copied())?;
+    Ok(missing
+        .iter()
+        .filter_map(|oid| versions.get(oid))
+        .flatten()
+        .cloned()
+        .collect())
+}
+
+/// Authors of the non-merge commits at most `window` older than `head_time`
+fn active_authors(repo: &Repository, head_time: i64, window: Window) -> Result<BTreeSet<String>> {
+    let cutoff = head_time - i64::from(window.days) * DAY_SECS;
+    let shallow = repo.is_shallow();
+    let mut walk = repo.revwalk()?;
+    walk.push_head()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+    let mut active = BTreeSet::new();
+    for oid in walk {
+        let commit = match oid.and_then(|oid| repo.find_commit(oid)) {
+            Ok(commit) => commit,
+            Err(_) if shallow => break,
+            Err(e) => return Err(e.into()),
+        };
+        if commit.parent_count() > 1 || commit.time().seconds() < cutoff {
+            continue;
+        }
+        active.insert(identity(&commit.author()));
+    }
+    Ok(active)
+}
+
+/// An author's email, lowercased; the name when there is no email
+fn identity(signature: &git2::Signature) -> String {
+    match signature.email().map(str::trim) {
+        Some(email) if !email.is_empty() => email.to_lowercase(),
+        _ => signature.name().unwrap_or_default().to_string(),
+    }
+}
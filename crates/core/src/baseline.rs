@@ -1,21 +1,161 @@
 //! Baseline/suppression — snapshot findings so only new ones are reported
+//!
+//! Entries can carry an expiry date, after which their findings resurface.
+//! [`entry_statuses`] checks each entry against the current findings and the
+//! files on disk, for `revet baseline review` and anything else that needs
+//! to know which entries are stale.
+//!
+//! Matching ignores lines, but each entry records its finding's line as an
+//! anchor. With `[baseline] auto_reconcile`, [`Baseline::reconcile`] moves
+//! the anchors of the files `--fix` edited and drops the entries whose line
+//! a fix deleted, so a stale entry can't go on hiding a real finding with
+//! the same message.
+//!
+//! [`delta`] tracks the entries added, fixed and expired between runs.
 
+pub mod delta;
+
+use crate::analyzer::registry::{prefix_of, source_of, Source};
+use crate::audit::MutationJournal;
+use crate::fixer::{Anchor, FixReport};
+use crate::redact::strip_masks;
+use crate::storage::StoragePaths;
 use crate::suppress::SuppressedFinding;
 use crate::Finding;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
-const BASELINE_FILE: &str = ".revet-cache/baseline.json";
-
-/// A single baselined finding, keyed by file + message (line-independent).
+/// A single baselined finding, keyed by file + message (line-independent)
+/// and the rule and source that produced it.
+///
+/// The message is the redacted form (see [`Finding::redacted_message`])
+/// without the masked secret values (see [`strip_masks`]), as it was before
+/// secret findings quoted them. Entries written with the values masked still
+/// match: the key strips them too.
+/// Entries from version 1 baselines have no rule or source; they match any
+/// finding with the same file and message.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct BaselineEntry {
     pub file: String,
     pub message: String,
+    /// Qualified sub-rule (`security:custom-acme-live`), else the finding
+    /// prefix (`SEC`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    /// Who produced the finding, so a renamed plugin or custom rule doesn't
+    /// match a built-in finding with the same message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<Source>,
+    /// In a file with a generated-code marker. Baselines always include
+    /// these findings, whatever `[files] generated` is, so changing the
+    /// policy later doesn't resurface them. Not part of the match key.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub generated: bool,
+    /// Date (`YYYY-MM-DD`) from which the entry no longer suppresses its
+    /// finding. Not part of the match key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    /// Line of the finding when it was baselined. Not part of the match key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+impl BaselineEntry {
+    /// The entry recording `finding`, its path relative to `repo_root`
+    pub fn of(finding: &Finding, repo_root: &Path) -> Self {
+        Self {
+            file: finding
+                .file
+                .strip_prefix(repo_root)
+                .unwrap_or(&finding.file)
+                .to_string_lossy()
+                .into_owned(),
+            message: strip_masks(finding.redacted_message()).into_owned(),
+            rule: Some(rule_id(finding).to_string()),
+            source: Some(source_of(finding)),
+            generated: finding.generated,
+            expires: None,
+            line: Some(finding.line),
+        }
+    }
+
+    fn key(&self) -> (&str, Cow<'_, str>, Option<(&str, &Source)>) {
+        let namespace = self.rule.as_deref().zip(self.source.as_ref());
+        (&self.file, strip_masks(&self.message), namespace)
+    }
+
+    /// Whether the entry has expired by `today` (`YYYY-MM-DD`)
+    pub fn is_expired(&self, today: &str) -> bool {
+        self.expires.as_deref().is_some_and(|date| date <= today)
+    }
+}
+
+/// Today's date in UTC, as `YYYY-MM-DD`
+pub fn today() -> String {
+    let secs = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    crate::sbom::rfc3339(secs)[..10].to_string()
+}
+
+/// Whether `date` is a calendar date written `YYYY-MM-DD`
+pub fn is_date(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('-').collect();
+    let [year, month, day] = parts[..] else {
+        return false;
+    };
+    let number = |s: &str, len: usize| {
+        (s.len() == len && s.bytes().all(|b| b.is_ascii_digit()))
+            .then(|| s.parse::<u32>().ok())
+            .flatten()
+    };
+    let (Some(year), Some(month), Some(day)) = (number(year, 4), number(month, 2), number(day, 2))
+    else {
+        return false;
+    };
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+/// Match key of `finding`: path relative to `repo_root`, redacted message
+/// without masked values, rule and source
+fn finding_key<'a>(
+    finding: &'a Finding,
+    repo_root: &Path,
+) -> (String, Cow<'a, str>, &'a str, Source) {
+    let rel = finding
+        .file
+        .strip_prefix(repo_root)
+        .unwrap_or(&finding.file)
+        .to_string_lossy()
+        .into_owned();
+    (
+        rel,
+        strip_masks(finding.redacted_message()),
+        rule_id(finding),
+        source_of(finding),
+    )
+}
+
+/// Rule id recorded for `finding` in a baseline
+fn rule_id(finding: &Finding) -> &str {
+    finding
+        .rule
+        .as_deref()
+        .unwrap_or_else(|| prefix_of(&finding.id))
 }
 
 /// Full baseline document stored on disk.
@@ -29,19 +169,12 @@ pub struct Baseline {
 }
 
 impl Baseline {
-    /// Build a baseline from a set of findings, relativizing paths against `repo_root`.
+    /// Build a baseline from a set of findings, relativizing paths against
+    /// `repo_root`. Entries are marked generated from [`Finding::generated`].
     pub fn from_findings(findings: &[Finding], repo_root: &Path, commit: Option<String>) -> Self {
         let entries: Vec<BaselineEntry> = findings
             .iter()
-            .map(|f| BaselineEntry {
-                file: f
-                    .file
-                    .strip_prefix(repo_root)
-                    .unwrap_or(&f.file)
-                    .to_string_lossy()
-                    .into_owned(),
-                message: f.message.clone(),
-            })
+            .map(|f| BaselineEntry::of(f, repo_root))
             .collect();
 
         let now = SystemTime::now()
@@ -50,7 +183,7 @@ impl Baseline {
             .unwrap_or_default();
 
         Baseline {
-            version: "1".to_string(),
+            version: "2".to_string(),
             created_at: now,
             commit,
             count: entries.len(),
@@ -59,66 +192,208 @@ impl Baseline {
     }
 
     /// Save the baseline to `.revet-cache/baseline.json`.
-    pub fn save(&self, repo_root: &Path) -> Result<()> {
-        let path = repo_root.join(BASELINE_FILE);
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)
-                .with_context(|| format!("creating cache dir {}", parent.display()))?;
-        }
+    pub fn save(&self, repo_root: &Path, journal: &mut MutationJournal) -> Result<()> {
+        self.save_at(&StoragePaths::in_repo(repo_root), journal)
+    }
+
+    /// Save the baseline to the baseline path of `paths`, atomically
+    /// (temp file + rename) so a reader never sees half a baseline
+    pub fn save_at(&self, paths: &StoragePaths, journal: &mut MutationJournal) -> Result<()> {
+        self.save_to(paths.baseline_path(), journal)
+    }
+
+    /// Save the baseline to `path`, atomically, recording the write in
+    /// `journal`
+    pub fn save_to(&self, path: &Path, journal: &mut MutationJournal) -> Result<()> {
         let json = serde_json::to_string_pretty(self).context("serializing baseline")?;
-        fs::write(&path, json).with_context(|| format!("writing {}", path.display()))?;
-        Ok(())
+        journal.replace(path, &json)
+    }
+
+    /// Carry the expiry dates of `previous` over to the entries with the
+    /// same match key, so re-creating a baseline keeps them
+    pub fn keep_expiry_from(&mut self, previous: &Baseline) {
+        let expiry: HashMap<_, &str> = previous
+            .entries
+            .iter()
+            .filter_map(|e| Some((e.key(), e.expires.as_deref()?)))
+            .collect();
+        let dates: Vec<Option<String>> = self
+            .entries
+            .iter()
+            .map(|e| expiry.get(&e.key()).map(|d| d.to_string()))
+            .collect();
+        for (entry, date) in self.entries.iter_mut().zip(dates) {
+            entry.expires = date;
+        }
+    }
+
+    /// Move the anchor lines of the entries in the files `report` edited to
+    /// where their text is after the fixes, dropping the entries whose line
+    /// a fix deleted (logged in the report). Returns whether any entry
+    /// changed.
+    pub fn reconcile(&mut self, report: &mut FixReport, repo_root: &Path) -> bool {
+        let before = self.entries.clone();
+        self.entries.retain_mut(|entry| {
+            let Some(line) = entry.line else {
+                return true;
+            };
+            let message = &entry.message;
+            let file = repo_root.join(&entry.file);
+            match report.remap(&file, line, || Anchor::BaselineEntry(message.clone())) {
+                Some(moved) => {
+                    entry.line = Some(moved);
+                    true
+                }
+                None => false,
+            }
+        });
+        self.count = self.entries.len();
+        self.entries != before
     }
 
     /// Load a baseline from disk, returning `None` if the file doesn't exist.
     pub fn load(repo_root: &Path) -> Result<Option<Self>> {
-        let path = repo_root.join(BASELINE_FILE);
+        Self::load_at(&StoragePaths::in_repo(repo_root))
+    }
+
+    /// Load the baseline of `paths`, falling back to the legacy in-repo
+    /// file; `None` if neither exists
+    pub fn load_at(paths: &StoragePaths) -> Result<Option<Self>> {
+        Self::load_from(&paths.baseline_read_path())
+    }
+
+    /// Load the baseline at `path`; `None` if it doesn't exist
+    pub fn load_from(path: &Path) -> Result<Option<Self>> {
         if !path.exists() {
             return Ok(None);
         }
         let data =
-            fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
         let baseline: Baseline =
             serde_json::from_str(&data).with_context(|| format!("parsing {}", path.display()))?;
         Ok(Some(baseline))
     }
 
+    /// Whether each of `findings` is already in this baseline (see
+    /// [`filter_findings`] for the match rules)
+    pub fn known(&self, findings: &[Finding], repo_root: &Path) -> Vec<bool> {
+        let today = today();
+        let lookup: HashSet<_> = self
+            .entries
+            .iter()
+            .filter(|e| !e.is_expired(&today))
+            .map(BaselineEntry::key)
+            .collect();
+        findings
+            .iter()
+            .map(|f| {
+                let (rel, message, rule, source) = finding_key(f, repo_root);
+                let namespaced = (rel.as_str(), message.clone(), Some((rule, &source)));
+                lookup.contains(&namespaced) || lookup.contains(&(rel.as_str(), message, None))
+            })
+            .collect()
+    }
+
     /// Delete the baseline file. Returns `true` if a file was actually removed.
-    pub fn clear(repo_root: &Path) -> Result<bool> {
-        let path = repo_root.join(BASELINE_FILE);
-        if path.exists() {
-            fs::remove_file(&path).with_context(|| format!("removing {}", path.display()))?;
-            Ok(true)
-        } else {
-            Ok(false)
+    pub fn clear(repo_root: &Path, journal: &mut MutationJournal) -> Result<bool> {
+        Self::clear_at(&StoragePaths::in_repo(repo_root), journal)
+    }
+
+    /// Delete the baseline of `paths` — the file in effect, so a legacy
+    /// in-repo baseline is removed when it is the one being read
+    pub fn clear_at(paths: &StoragePaths, journal: &mut MutationJournal) -> Result<bool> {
+        journal.remove(&paths.baseline_read_path())
+    }
+}
+
+/// Where a baseline entry stands against the current tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryStatus {
+    /// A current finding matches the entry
+    Matched,
+    /// The file still exists but no current finding matches
+    Unmatched,
+    /// The entry's file no longer exists
+    FileDeleted,
+}
+
+impl EntryStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EntryStatus::Matched => "still matches",
+            EntryStatus::Unmatched => "no longer matches",
+            EntryStatus::FileDeleted => "file deleted",
         }
     }
 }
 
+/// Status of one baseline entry, see [`entry_statuses`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryMatch {
+    pub status: EntryStatus,
+    /// Index in the findings of the first one the entry matches
+    pub finding: Option<usize>,
+}
+
+/// Check each entry of `baseline` against the current `findings` (with the
+/// match rules of [`filter_findings`], ignoring expiry) and the files under
+/// `repo_root`. Returns one [`EntryMatch`] per entry, in order.
+pub fn entry_statuses(
+    baseline: &Baseline,
+    findings: &[Finding],
+    repo_root: &Path,
+) -> Vec<EntryMatch> {
+    let mut namespaced: HashMap<(String, Cow<str>, &str, Source), usize> = HashMap::new();
+    let mut legacy: HashMap<(String, Cow<str>), usize> = HashMap::new();
+    for (i, f) in findings.iter().enumerate() {
+        let (rel, message, rule, source) = finding_key(f, repo_root);
+        legacy.entry((rel.clone(), message.clone())).or_insert(i);
+        namespaced.entry((rel, message, rule, source)).or_insert(i);
+    }
+
+    baseline
+        .entries
+        .iter()
+        .map(|entry| {
+            let message = strip_masks(&entry.message);
+            let finding = match (&entry.rule, &entry.source) {
+                (Some(rule), Some(source)) => namespaced
+                    .get(&(entry.file.clone(), message, rule.as_str(), source.clone()))
+                    .copied(),
+                _ => legacy.get(&(entry.file.clone(), message)).copied(),
+            };
+            let status = if finding.is_some() {
+                EntryStatus::Matched
+            } else if !repo_root.join(PathBuf::from(&entry.file)).exists() {
+                EntryStatus::FileDeleted
+            } else {
+                EntryStatus::Unmatched
+            };
+            EntryMatch { status, finding }
+        })
+        .collect()
+}
+
 /// Filter findings against a baseline.
 ///
+/// A finding is baselined when an entry has its file, message, rule and
+/// source, or its file and message for legacy entries without the latter.
+/// Expired entries match nothing.
+///
 /// Returns `(new_findings, suppressed)`.
 pub fn filter_findings(
     findings: Vec<Finding>,
     baseline: &Baseline,
     repo_root: &Path,
 ) -> (Vec<Finding>, Vec<SuppressedFinding>) {
-    let lookup: HashSet<(&str, &str)> = baseline
-        .entries
-        .iter()
-        .map(|e| (e.file.as_str(), e.message.as_str()))
-        .collect();
+    let known = baseline.known(&findings, repo_root);
 
     let mut new_findings = Vec::new();
     let mut suppressed: Vec<SuppressedFinding> = Vec::new();
 
-    for f in findings {
-        let rel = f
-            .file
-            .strip_prefix(repo_root)
-            .unwrap_or(&f.file)
-            .to_string_lossy();
-        if lookup.contains(&(rel.as_ref(), f.message.as_str())) {
+    for (f, known) in findings.into_iter().zip(known) {
+        if known {
             suppressed.push(SuppressedFinding {
                 finding: f,
                 reason: "baseline".to_string(),
@@ -0,0 +1,265 @@
+//! Baseline debt over time — the entries added, fixed and expired between
+//! two runs, per owning team
+//!
+//! A [`Snapshot`] records every entry of a baseline by its match key, with
+//! the state [`entry_statuses`] gives it: live while a finding still matches
+//! and the entry has not expired, fixed once nothing matches or its file is
+//! gone (the "no longer matches" and "file deleted" of `revet baseline
+//! review`). [`Delta::between`] compares two snapshots:
+//!
+//! - **added** — live now, not live before
+//! - **removed** — live before, now fixed or no longer in the baseline
+//! - **expired** — live before, now past its expiry date with its finding
+//!   still there
+//!
+//! so `added - removed - expired` is the change in live entries. Each entry
+//! is attributed to the first team (`@org/team`) `CODEOWNERS` lists for its
+//! file, else the first owner, else [`UNOWNED`].
+
+use super::{entry_statuses, Baseline, BaselineEntry, EntryMatch, EntryStatus};
+use crate::analyzer::ownership::codeowners::CodeOwners;
+use crate::redact::strip_masks;
+use crate::Finding;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Owner of entries in files `CODEOWNERS` assigns to no one
+pub const UNOWNED: &str = "(unowned)";
+
+/// Where a baseline entry stands in a [`Snapshot`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DebtState {
+    /// A finding matches and the entry has not expired
+    Live,
+    /// A finding matches but the entry has expired
+    Expired,
+    /// No finding matches, or the file is gone
+    Fixed,
+}
+
+/// One baseline entry in a [`Snapshot`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    /// Path relative to the repository root
+    pub file: String,
+    /// Hash of the entry's match key (see [`entry_key`])
+    pub key: String,
+    pub state: DebtState,
+}
+
+/// The entries of a baseline and their states at one run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub entries: Vec<SnapshotEntry>,
+}
+
+impl Snapshot {
+    /// The snapshot of `baseline` with `statuses` from [`entry_statuses`],
+    /// expiry checked against `today` (`YYYY-MM-DD`)
+    pub fn new(baseline: &Baseline, statuses: &[EntryMatch], today: &str) -> Self {
+        let entries = baseline
+            .entries
+            .iter()
+            .zip(statuses)
+            .map(|(entry, status)| SnapshotEntry {
+                file: entry.file.clone(),
+                key: entry_key(entry),
+                state: match status.status {
+                    EntryStatus::Unmatched | EntryStatus::FileDeleted => DebtState::Fixed,
+                    EntryStatus::Matched if entry.is_expired(today) => DebtState::Expired,
+                    EntryStatus::Matched => DebtState::Live,
+                },
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// The snapshot of `baseline` against the findings of a full scan
+    pub fn of(baseline: &Baseline, findings: &[Finding], repo_root: &Path, today: &str) -> Self {
+        Self::new(
+            baseline,
+            &entry_statuses(baseline, findings, repo_root),
+            today,
+        )
+    }
+
+    /// Live entries
+    pub fn live(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.state == DebtState::Live)
+            .count()
+    }
+}
+
+/// Changes to one owner's live entries; serialized with [`OwnerDelta::net`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct OwnerDelta {
+    pub owner: String,
+    pub added: usize,
+    pub removed: usize,
+    pub expired: usize,
+}
+
+impl OwnerDelta {
+    /// Change in live entries: negative when debt went down
+    pub fn net(&self) -> i64 {
+        self.added as i64 - self.removed as i64 - self.expired as i64
+    }
+
+    fn add(&mut self, other: &OwnerDelta) {
+        self.added += other.added;
+        self.removed += other.removed;
+        self.expired += other.expired;
+    }
+}
+
+impl Serialize for OwnerDelta {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut s = serializer.serialize_struct("OwnerDelta", 5)?;
+        s.serialize_field("owner", &self.owner)?;
+        s.serialize_field("added", &self.added)?;
+        s.serialize_field("removed", &self.removed)?;
+        s.serialize_field("expired", &self.expired)?;
+        s.serialize_field("net", &self.net())?;
+        s.end()
+    }
+}
+
+/// Changes to live entries between two snapshots, per owner
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Delta {
+    /// Owners with any change, by name
+    pub owners: Vec<OwnerDelta>,
+}
+
+impl Delta {
+    /// What changed from `previous` to `current`, each entry attributed
+    /// with `owners`
+    pub fn between(previous: &Snapshot, current: &Snapshot, owners: &CodeOwners) -> Self {
+        let before: HashMap<&str, DebtState> = previous
+            .entries
+            .iter()
+            .map(|e| (e.key.as_str(), e.state))
+            .collect();
+        let after: HashMap<&str, DebtState> = current
+            .entries
+            .iter()
+            .map(|e| (e.key.as_str(), e.state))
+            .collect();
+        let mut by_owner: BTreeMap<String, OwnerDelta> = BTreeMap::new();
+        let mut count = |file: &str, change: fn(&mut OwnerDelta)| {
+            let owner = owner_of(owners, file);
+            change(by_owner.entry(owner.clone()).or_insert(OwnerDelta {
+                owner,
+                ..Default::default()
+            }));
+        };
+
+        for entry in &current.entries {
+            let was_live = before.get(entry.key.as_str()) == Some(&DebtState::Live);
+            if entry.state == DebtState::Live && !was_live {
+                count(&entry.file, |d| d.added += 1);
+            }
+        }
+        for entry in &previous.entries {
+            if entry.state != DebtState::Live {
+                continue;
+            }
+            match after.get(entry.key.as_str()) {
+                None | Some(DebtState::Fixed) => count(&entry.file, |d| d.removed += 1),
+                Some(DebtState::Expired) => count(&entry.file, |d| d.expired += 1),
+                Some(DebtState::Live) => {}
+            }
+        }
+        Self {
+            owners: by_owner.into_values().collect(),
+        }
+    }
+
+    /// `dropped`, entries taken out of the baseline, each counted as removed
+    pub fn dropped(dropped: &[BaselineEntry], owners: &CodeOwners) -> Self {
+        let mut delta = Self::default();
+        for entry in dropped {
+            delta.add(&Self {
+                owners: vec![OwnerDelta {
+                    owner: owner_of(owners, &entry.file),
+                    removed: 1,
+                    ..Default::default()
+                }],
+            });
+        }
+        delta
+    }
+
+    /// Add `other`'s changes, owner by owner
+    pub fn add(&mut self, other: &Delta) {
+        let mut by_owner: BTreeMap<String, OwnerDelta> = std::mem::take(&mut self.owners)
+            .into_iter()
+            .map(|d| (d.owner.clone(), d))
+            .collect();
+        for delta in &other.owners {
+            by_owner
+                .entry(delta.owner.clone())
+                .or_insert_with(|| OwnerDelta {
+                    owner: delta.owner.clone(),
+                    ..Default::default()
+                })
+                .add(delta);
+        }
+        self.owners = by_owner.into_values().collect();
+    }
+
+    /// All owners' changes together, under the owner `total`
+    pub fn total(&self) -> OwnerDelta {
+        let mut total = OwnerDelta {
+            owner: "total".to_string(),
+            ..Default::default()
+        };
+        for delta in &self.owners {
+            total.add(delta);
+        }
+        total
+    }
+
+    /// Whether no entry changed
+    pub fn is_empty(&self) -> bool {
+        self.owners.is_empty()
+    }
+}
+
+/// The owner an entry in `file` is attributed to
+pub fn owner_of(owners: &CodeOwners, file: &str) -> String {
+    let listed = owners.owners(&file.replace('\\', "/"));
+    listed
+        .iter()
+        .find(|owner| owner.starts_with('@') && owner.contains('/'))
+        .or_else(|| listed.first())
+        .map_or_else(|| UNOWNED.to_string(), String::clone)
+}
+
+/// Hash of `entry`'s match key: file, message, rule and source
+pub fn entry_key(entry: &BaselineEntry) -> String {
+    let rule = entry.rule.as_deref().unwrap_or_default();
+    let source = entry
+        .source
+        .as_ref()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+    let mut hasher = Sha256::new();
+    let message = strip_masks(&entry.message);
+    for part in [entry.file.as_str(), &message, rule, &source] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
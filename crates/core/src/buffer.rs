@@ -0,0 +1,198 @@
+//! In-memory analysis of unsaved editor buffers
+//!
+//! Editor integrations need findings for the buffer as typed, not the file on
+//! disk. [`analyze_buffer`] runs the content analyzers and the file-local
+//! graph checks (complexity, dead imports, unused exports) against a string,
+//! using a [`GraphContext`] built once per repository for cross-file context.
+//!
+//! Each call swaps the buffer into a copy of the context graph the way watch
+//! mode swaps an edited file ([`IncrementalGraph`]): the file's prior nodes
+//! are removed ([`CodeGraph::remove_file`]), the buffer is parsed from memory
+//! and merged in, and [`CrossFileResolver::resolve_incremental`] re-links
+//! what touches it from the records every file was resolved with. Nothing is
+//! persisted — the file on disk and `.revet-cache/` are never touched, and the
+//! per-call graph is dropped before returning.
+//!
+//! The C# passes are not re-run: in a graph with C# files the buffer's own
+//! imports and calls are resolved, but calls from other files into it are
+//! not re-linked.
+//!
+//! [`IncrementalGraph`]: crate::parser::incremental::IncrementalGraph
+//! [`CrossFileResolver::resolve_incremental`]: crate::parser::resolver::CrossFileResolver::resolve_incremental
+
+use crate::analyzer::AnalyzerDispatcher;
+use crate::cache::memo::AnalysisMemo;
+use crate::cache::FileGraphCache;
+use crate::config::RevetConfig;
+use crate::discovery::discover_files;
+use crate::finding::Finding;
+use crate::graph::CodeGraph;
+use crate::parser::{ParseState, ParserDispatcher};
+use crate::storage::StoragePaths;
+use crate::suppress::{matches_finding, resolve_suppressions, Constructs};
+use anyhow::Result;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Repository-wide context shared by every [`analyze_buffer`] call.
+///
+/// Holds the parsed graph, the parse records it was resolved with and the
+/// dispatchers built from the config. Build it once (e.g. when `revet serve`
+/// opens a workspace) and reuse it per `didChange`; it is never mutated by
+/// buffer analysis.
+pub struct GraphContext {
+    repo_root: PathBuf,
+    graph: CodeGraph,
+    /// Every file's unresolved imports and calls, NodeIds pointing into
+    /// `graph`; empty for a graph with C# files
+    records: ParseState,
+    parsers: ParserDispatcher,
+    analyzers: AnalyzerDispatcher,
+}
+
+impl GraphContext {
+    /// Discover and parse the repository, reusing the per-file parse cache
+    /// and adding the fragments of files it has not seen
+    pub fn load(repo_root: &Path, config: &RevetConfig) -> Result<Self> {
+        let repo_root = repo_root.canonicalize()?;
+        let file_cache = FileGraphCache::at(&StoragePaths::resolve(&repo_root, config));
+        Self::load_with(&repo_root, config, &file_cache)
+    }
+
+    /// [`GraphContext::load`] through `file_cache`, e.g. one that is
+    /// [read-only](FileGraphCache::read_only) or disabled
+    pub fn load_with(
+        repo_root: &Path,
+        config: &RevetConfig,
+        file_cache: &FileGraphCache,
+    ) -> Result<Self> {
+        let repo_root = repo_root.canonicalize()?;
+        let parsers = ParserDispatcher::new_with_config(config);
+        let files = discover_files(
+            &repo_root,
+            &parsers.supported_extensions(),
+            &config.ignore.paths,
+        )?;
+        let (graph, mut records, _errors) =
+            parsers.parse_files_incremental_with_records(&files, repo_root.clone(), file_cache);
+        // Merging C# partial types moves NodeIds the records still point at
+        if files
+            .iter()
+            .any(|file| file.extension().and_then(|e| e.to_str()) == Some("cs"))
+        {
+            records = ParseState::default();
+        }
+        Ok(Self {
+            repo_root,
+            graph,
+            records,
+            parsers,
+            analyzers: AnalyzerDispatcher::new_with_config(config)
+                .with_memo(AnalysisMemo::session()),
+        })
+    }
+
+    /// Repository root all relative buffer paths are resolved against
+    pub fn repo_root(&self) -> &Path {
+        &self.repo_root
+    }
+
+    /// The on-disk repository graph
+    pub fn graph(&self) -> &CodeGraph {
+        &self.graph
+    }
+}
+
+/// Analyze `contents` as the current text of `path`.
+///
+/// Findings carry `path` (made absolute against the context's repo root), so
+/// diff filtering and per-path rules apply as for on-disk files. Inline
+/// `revet-ignore` comments are read from `contents`, not from disk.
+pub fn analyze_buffer(
+    path: &Path,
+    contents: &str,
+    config: &RevetConfig,
+    graph_ctx: &GraphContext,
+) -> Vec<Finding> {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        graph_ctx.repo_root.join(path)
+    };
+
+    let overlay = overlay_graph(graph_ctx, &path, contents);
+    let findings = graph_ctx.analyzers.run_buffer(
+        &path,
+        contents,
+        overlay.as_ref(),
+        &graph_ctx.repo_root,
+        config,
+    );
+    let constructs = overlay
+        .as_ref()
+        .map(Constructs::from_graph)
+        .unwrap_or_default();
+    drop(overlay);
+
+    let suppressions = resolve_suppressions(&path, contents, constructs.of(&path));
+    if suppressions.is_empty() {
+        return findings;
+    }
+    findings
+        .into_iter()
+        .filter(|f| {
+            !suppressions
+                .iter()
+                .any(|s| s.covers.contains(&f.line) && matches_finding(f, &s.rules))
+        })
+        .collect()
+}
+
+/// Build the per-call graph with `path`'s nodes replaced by `contents`.
+///
+/// Returns `None` when no parser handles `path` or the buffer fails to parse;
+/// content analyzers still run in that case.
+fn overlay_graph(ctx: &GraphContext, path: &Path, contents: &str) -> Option<CodeGraph> {
+    let parser = ctx.parsers.find_parser(path)?;
+
+    let mut local = CodeGraph::new(ctx.repo_root.clone());
+    let (_, mut state) = parser
+        .parse_source_with_state(contents, path, &mut local)
+        .ok()?;
+
+    // Swap the file's nodes for the buffer's, as watch mode does on an edit
+    let mut graph = ctx.graph.clone();
+    let moved = graph.remove_file(path);
+    let mut records = ParseState {
+        unresolved_imports: ctx
+            .records
+            .unresolved_imports
+            .iter()
+            .filter(|import| import.importing_file != path)
+            .cloned()
+            .collect(),
+        unresolved_calls: ctx
+            .records
+            .unresolved_calls
+            .iter()
+            .filter(|call| call.importing_file != path)
+            .cloned()
+            .collect(),
+        ..Default::default()
+    };
+    records.remap(&moved);
+
+    let id_map = graph.merge(local);
+    state.remap(&id_map);
+    records.unresolved_imports.extend(state.unresolved_imports);
+    records.unresolved_calls.extend(state.unresolved_calls);
+
+    ctx.parsers.resolver(&ctx.repo_root).resolve_incremental(
+        &mut graph,
+        records.unresolved_imports,
+        records.unresolved_calls,
+        &HashSet::from([path.to_path_buf()]),
+    );
+
+    Some(graph)
+}
@@ -1,13 +1,37 @@
 //! Graph caching for incremental analysis
-
-use crate::graph::CodeGraph;
+//!
+//! The whole-repo graph is stored as msgpack by default. Very large graphs can
+//! use the [`compact`] format instead (`[cache] format = "compact"`), which is
+//! memory-mapped on load rather than deserialized up front.
+//!
+//! With `[cache.remote]` configured, the per-file parse cache and the
+//! [`AnalysisCache`] of domain analyzer findings are also shared through a
+//! [`remote::RemoteCache`].
+//!
+//! Long-lived processes also keep analyzer findings in [`memo`].
+
+pub mod compact;
+pub mod memo;
+pub mod remote;
+
+use crate::config::{CacheConfig, RevetConfig};
+use crate::finding::Finding;
+use crate::graph::{CodeGraph, NodeData, NodeId};
+use crate::i18n::Msg;
 use crate::parser::ParseState;
+use crate::storage::{StoragePaths, ANALYSIS_DIR, FILES_DIR};
 use anyhow::{Context, Result};
+use remote::RemoteCache;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use std::time::SystemTime;
 
+/// Cache metadata, next to the graph file
+const META_FILE: &str = "graph.meta.json";
+
 /// Metadata about a cached graph
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphCacheMeta {
@@ -24,16 +48,77 @@ pub struct GraphCacheMeta {
     pub revet_version: String,
 }
 
+/// On-disk format of the whole-repo graph cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CacheFormat {
+    /// `graph.msgpack` — serde msgpack of the full graph
+    #[default]
+    Msgpack,
+    /// `graph.compact` — see [`compact`]
+    Compact,
+}
+
+impl CacheFormat {
+    /// Format selected by `[cache] format` (unknown values fall back to msgpack)
+    pub fn from_config(config: &CacheConfig) -> Self {
+        match config.format.as_str() {
+            "compact" => CacheFormat::Compact,
+            _ => CacheFormat::Msgpack,
+        }
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            CacheFormat::Msgpack => "graph.msgpack",
+            CacheFormat::Compact => "graph.compact",
+        }
+    }
+}
+
 /// Manages graph caching for incremental analysis
 pub struct GraphCache {
     cache_dir: PathBuf,
+    /// Read when `cache_dir` holds no cached graph yet
+    legacy_dir: Option<PathBuf>,
+    format: CacheFormat,
 }
 
 impl GraphCache {
-    /// Create a new graph cache manager
+    /// Create a new graph cache manager using the default (msgpack) format
     pub fn new(repo_root: &Path) -> Self {
+        Self::with_format(repo_root, CacheFormat::default())
+    }
+
+    /// Create a graph cache manager in `<repo_root>/.revet-cache` that reads
+    /// and writes `format`
+    pub fn with_format(repo_root: &Path, format: CacheFormat) -> Self {
+        Self::at(&StoragePaths::in_repo(repo_root), format)
+    }
+
+    /// Create a graph cache manager in the data directory of `paths`
+    pub fn at(paths: &StoragePaths, format: CacheFormat) -> Self {
         Self {
-            cache_dir: repo_root.join(".revet-cache"),
+            cache_dir: paths.data_dir().to_path_buf(),
+            legacy_dir: paths
+                .is_relocated()
+                .then(|| paths.legacy_dir().to_path_buf()),
+            format,
+        }
+    }
+
+    /// Path of the graph file for the configured format
+    pub fn graph_path(&self) -> PathBuf {
+        self.cache_dir.join(self.format.file_name())
+    }
+
+    /// Directory to load from: the cache directory, else the legacy one
+    /// when only it holds a cached graph
+    fn read_dir(&self) -> &Path {
+        let complete =
+            |dir: &Path| dir.join(self.format.file_name()).exists() && dir.join(META_FILE).exists();
+        match &self.legacy_dir {
+            Some(legacy) if !complete(&self.cache_dir) && complete(legacy) => legacy,
+            _ => &self.cache_dir,
         }
     }
 
@@ -43,10 +128,16 @@ impl GraphCache {
         Ok(())
     }
 
-    /// Load a cached graph if it exists
+    /// Load a cached graph if it exists.
+    ///
+    /// Graphs cached before node fingerprints existed get them assigned on
+    /// load. A compact file that fails validation (version or checksum mismatch,
+    /// truncation) is removed and reported as an error, so callers fall back
+    /// to rebuilding the graph and the next save writes a fresh file.
     pub fn load(&self) -> Result<Option<(CodeGraph, GraphCacheMeta)>> {
-        let graph_path = self.cache_dir.join("graph.msgpack");
-        let meta_path = self.cache_dir.join("graph.meta.json");
+        let dir = self.read_dir();
+        let graph_path = dir.join(self.format.file_name());
+        let meta_path = dir.join(META_FILE);
 
         if !graph_path.exists() || !meta_path.exists() {
             return Ok(None);
@@ -57,8 +148,23 @@ impl GraphCache {
         let meta: GraphCacheMeta = serde_json::from_str(&meta_contents)?;
 
         // Load graph
-        let graph_contents = std::fs::read(&graph_path)?;
-        let graph: CodeGraph = rmp_serde::from_slice(&graph_contents)?;
+        let graph = match self.format {
+            CacheFormat::Msgpack => {
+                let graph_contents = std::fs::read(&graph_path)?;
+                rmp_serde::from_slice(&graph_contents)?
+            }
+            CacheFormat::Compact => {
+                match compact::CompactGraph::open(&graph_path).and_then(|c| c.to_graph()) {
+                    Ok(graph) => graph,
+                    Err(e) => {
+                        let _ = std::fs::remove_file(&graph_path);
+                        return Err(e);
+                    }
+                }
+            }
+        };
+        let mut graph: CodeGraph = graph;
+        graph.ensure_fingerprints();
 
         Ok(Some((graph, meta)))
     }
@@ -67,16 +173,21 @@ impl GraphCache {
     pub fn save(&self, graph: &CodeGraph, meta: &GraphCacheMeta) -> Result<()> {
         self.ensure_cache_dir()?;
 
-        let graph_path = self.cache_dir.join("graph.msgpack");
-        let meta_path = self.cache_dir.join("graph.meta.json");
+        let graph_path = self.graph_path();
+        let meta_path = self.cache_dir.join(META_FILE);
 
         // Save metadata
         let meta_contents = serde_json::to_string_pretty(meta)?;
         std::fs::write(&meta_path, meta_contents)?;
 
         // Save graph
-        let graph_contents = rmp_serde::to_vec(graph)?;
-        std::fs::write(&graph_path, graph_contents)?;
+        match self.format {
+            CacheFormat::Msgpack => {
+                let graph_contents = rmp_serde::to_vec(graph)?;
+                std::fs::write(&graph_path, graph_contents)?;
+            }
+            CacheFormat::Compact => compact::write(graph, &graph_path)?,
+        }
 
         Ok(())
     }
@@ -84,7 +195,7 @@ impl GraphCache {
     /// Save only the metadata JSON (when graph is persisted via CozoStore)
     pub fn save_meta(&self, meta: &GraphCacheMeta) -> Result<()> {
         self.ensure_cache_dir()?;
-        let meta_path = self.cache_dir.join("graph.meta.json");
+        let meta_path = self.cache_dir.join(META_FILE);
         let meta_contents = serde_json::to_string_pretty(meta)?;
         std::fs::write(&meta_path, meta_contents)?;
         Ok(())
@@ -109,15 +220,11 @@ impl GraphCache {
             .unwrap_or(true))
     }
 
-    /// Compute a checksum for a file
+    /// Compute a checksum for a file: the hex SHA-256 of its contents,
+    /// stable across Rust releases and platforms
     pub fn compute_file_checksum(file_path: &Path) -> Result<String> {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
         let contents = std::fs::read(file_path)?;
-        let mut hasher = DefaultHasher::new();
-        contents.hash(&mut hasher);
-        Ok(format!("{:x}", hasher.finish()))
+        Ok(format!("{:x}", Sha256::digest(&contents)))
     }
 
     /// Get the current Git commit hash
@@ -215,31 +322,131 @@ impl GraphCache {
 
 // ── Per-file graph fragment cache ────────────────────────────────────────────
 
+/// Version of the fragment entries, part of every file name and remote ID;
+/// bumped when their keys or layout change so older entries are never read
+/// (and are evicted by the next full run)
+const FRAGMENT_VERSION: u32 = 2;
+
+/// Name of the fragment of `content_hash`, without extension
+fn fragment_id(content_hash: &str) -> String {
+    format!("{}.v{}", content_hash, FRAGMENT_VERSION)
+}
+
 /// Serialized payload for a single file's parse result.
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 struct FileFragment {
     graph: CodeGraph,
     state: ParseState,
 }
 
+impl FileFragment {
+    /// A copy without any path, as the remote cache holds it: shared by
+    /// every job and repository, it is only ever applied to the file being
+    /// parsed (see [`FileFragment::attached`])
+    fn detached(&self) -> Self {
+        let mut fragment = self.attached(Path::new(""), Path::new(""));
+        let ids: Vec<NodeId> = fragment.graph.nodes().map(|(id, _)| id).collect();
+        for id in ids {
+            if let Some(NodeData::Import { resolved_path, .. }) =
+                fragment.graph.node_mut(id).map(|n| n.data_mut())
+            {
+                *resolved_path = None;
+            }
+        }
+        fragment
+    }
+
+    /// This fragment as the parse of `file` under `root`: a fragment holds
+    /// the nodes of one file, so every node and import site is moved to it
+    fn attached(&self, root: &Path, file: &Path) -> Self {
+        let graph = self
+            .graph
+            .relocated(root.to_path_buf(), |_| file.to_path_buf());
+        let mut state = self.state.clone();
+        if state.source_file.is_some() {
+            state.source_file = Some(file.to_path_buf());
+        }
+        for import in &mut state.unresolved_imports {
+            import.importing_file = file.to_path_buf();
+        }
+        for call in &mut state.unresolved_calls {
+            call.importing_file = file.to_path_buf();
+        }
+        Self { graph, state }
+    }
+}
+
+/// Fragments held in memory across runs of a long-lived process, keyed by
+/// fragment path; `None` until [`FileGraphCache::keep_resident`]
+static RESIDENT: Mutex<Option<HashMap<PathBuf, FileFragment>>> = Mutex::new(None);
+
+fn resident() -> std::sync::MutexGuard<'static, Option<HashMap<PathBuf, FileFragment>>> {
+    RESIDENT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
 /// Cache for per-file `(CodeGraph, ParseState)` fragments.
 ///
-/// Each entry is keyed by the hex content-hash of the source file and stored
-/// as a msgpack file in `.revet-cache/files/`. On a subsequent run, unchanged
-/// files are loaded from cache instead of being re-parsed by tree-sitter,
-/// giving near-instant re-runs for large repos where only a few files changed.
+/// Each entry is keyed by [`FileGraphCache::key`], the hash of the source
+/// file's path in the repository and its content, and stored
+/// as a msgpack file in `files/` of the state directory (see
+/// [`StoragePaths`]). On a subsequent run, unchanged files are loaded from
+/// cache instead of being re-parsed by tree-sitter, giving near-instant
+/// re-runs for large repos where only a few files changed. Fragments of
+/// content no longer in the tree are removed by [`FileGraphCache::evict_unused`].
 pub struct FileGraphCache {
     cache_dir: PathBuf,
+    /// Also read on a miss, until the legacy cache is migrated
+    legacy_dir: Option<PathBuf>,
+    /// Asked on a local miss, and sent every fragment saved
+    remote: Option<Arc<RemoteCache>>,
+    /// Off with `--no-cache`: nothing is read or written
+    enabled: bool,
+    /// Fragments are read but none is written
+    read_only: bool,
+    /// Fragment IDs looked up so far
+    used: Mutex<HashSet<String>>,
 }
 
 impl FileGraphCache {
     /// Create a file graph cache rooted at `<repo_root>/.revet-cache/files/`.
     pub fn new(repo_root: &Path) -> Self {
+        Self::at(&StoragePaths::in_repo(repo_root))
+    }
+
+    /// Create a file graph cache in the data directory of `paths`
+    pub fn at(paths: &StoragePaths) -> Self {
         Self {
-            cache_dir: repo_root.join(".revet-cache").join("files"),
+            cache_dir: paths.write_path(FILES_DIR),
+            legacy_dir: paths
+                .is_relocated()
+                .then(|| paths.legacy_dir().join(FILES_DIR)),
+            remote: None,
+            enabled: true,
+            read_only: false,
+            used: Mutex::new(HashSet::new()),
         }
     }
 
+    /// Read and write no fragments when `enabled` is false, so every file
+    /// is parsed (`--no-cache`)
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Read fragments but write none, not even those fetched from the
+    /// remote, for runs that must leave the state directory as it is
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Share fragments through `remote` as well
+    pub fn with_remote(mut self, remote: Option<Arc<RemoteCache>>) -> Self {
+        self.remote = remote;
+        self
+    }
+
     fn ensure_dir(&self) -> Result<()> {
         std::fs::create_dir_all(&self.cache_dir)
             .context("Failed to create file graph cache directory")?;
@@ -247,31 +454,294 @@ impl FileGraphCache {
     }
 
     fn path_for(&self, content_hash: &str) -> PathBuf {
-        self.cache_dir.join(format!("{}.msgpack", content_hash))
+        self.cache_dir
+            .join(format!("{}.msgpack", fragment_id(content_hash)))
     }
 
-    /// Load a cached `(CodeGraph, ParseState)` for a file with the given
-    /// content hash. Returns `None` on any miss or deserialization failure
-    /// so callers always fall back to a fresh parse.
-    pub fn load(&self, content_hash: &str) -> Option<(CodeGraph, ParseState)> {
-        let path = self.path_for(content_hash);
-        let bytes = std::fs::read(&path).ok()?;
-        let fragment: FileFragment = rmp_serde::from_slice(&bytes).ok()?;
+    /// Key of the fragment of `file` under `root`: the hex SHA-256 of its
+    /// repo-relative path and its content. Files with the same content at
+    /// other paths get entries of their own, since the file node's name,
+    /// its code kind and Go build constraints depend on the path.
+    pub fn key(root: &Path, file: &Path) -> Result<String> {
+        let contents = std::fs::read(file)?;
+        let relative = file.strip_prefix(root).unwrap_or(file);
+        let mut hasher = Sha256::new();
+        hasher.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+        hasher.update([0]);
+        hasher.update(&contents);
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Keep every fragment loaded or saved from now on in memory, so later
+    /// runs in this process skip reading and decoding it (`revet daemon`)
+    pub fn keep_resident() {
+        resident().get_or_insert_with(HashMap::new);
+    }
+
+    /// Drop the fragments held in memory, returning how many there were;
+    /// they are still kept from now on
+    pub fn clear_resident() -> usize {
+        resident().as_mut().map_or(0, |map| {
+            let count = map.len();
+            map.clear();
+            count
+        })
+    }
+
+    /// Number of fragments held in memory
+    pub fn resident_count() -> usize {
+        resident().as_ref().map_or(0, HashMap::len)
+    }
+
+    fn remember(&self, content_hash: &str, fragment: &FileFragment) {
+        if let Some(map) = resident().as_mut() {
+            map.insert(self.path_for(content_hash), fragment.clone());
+        }
+    }
+
+    /// Load the cached `(CodeGraph, ParseState)` of `file` under `root`,
+    /// stored under `content_hash` (see [`FileGraphCache::key`]), with its
+    /// paths pointing at `file`. Returns `None` on any miss or
+    /// deserialization failure so callers always fall back to a fresh
+    /// parse. A fragment found only in the remote cache is kept locally.
+    pub fn load(
+        &self,
+        content_hash: &str,
+        root: &Path,
+        file: &Path,
+    ) -> Option<(CodeGraph, ParseState)> {
+        let fragment = self.fragment(content_hash)?.attached(root, file);
         Some((fragment.graph, fragment.state))
     }
 
-    /// Persist a `(CodeGraph, ParseState)` fragment keyed by content hash.
+    fn fragment(&self, content_hash: &str) -> Option<FileFragment> {
+        if !self.enabled {
+            return None;
+        }
+        self.used
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(fragment_id(content_hash));
+        if let Some(fragment) = resident()
+            .as_ref()
+            .and_then(|map| map.get(&self.path_for(content_hash)))
+        {
+            return Some(fragment.clone());
+        }
+        let name = format!("{}.msgpack", fragment_id(content_hash));
+        let local = std::fs::read(self.cache_dir.join(&name)).or_else(|e| match &self.legacy_dir {
+            Some(legacy) => std::fs::read(legacy.join(&name)),
+            None => Err(e),
+        });
+        if let Ok(bytes) = local {
+            let fragment: FileFragment = rmp_serde::from_slice(&bytes).ok()?;
+            self.remember(content_hash, &fragment);
+            return Some(fragment);
+        }
+
+        let remote = self.remote.as_ref()?;
+        let bytes = remote.get(remote::GRAPH, &fragment_id(content_hash))?;
+        let fragment: FileFragment = rmp_serde::from_slice(&bytes).ok()?;
+        if !self.read_only && self.ensure_dir().is_ok() {
+            if let Ok(bytes) = rmp_serde::to_vec(&fragment) {
+                let _ = std::fs::write(self.path_for(content_hash), bytes);
+            }
+        }
+        self.remember(content_hash, &fragment);
+        Some(fragment)
+    }
+
+    /// Persist a `(CodeGraph, ParseState)` fragment keyed by content hash,
+    /// and upload it, without any path, to the remote cache.
     /// Failures are silently ignored — caching is best-effort.
     pub fn save(&self, content_hash: &str, graph: &CodeGraph, state: &ParseState) {
-        if self.ensure_dir().is_err() {
+        if !self.enabled || self.read_only {
             return;
         }
         let fragment = FileFragment {
             graph: graph.clone(),
             state: state.clone(),
         };
-        if let Ok(bytes) = rmp_serde::to_vec(&fragment) {
-            let _ = std::fs::write(self.path_for(content_hash), bytes);
+        self.remember(content_hash, &fragment);
+        let Ok(bytes) = rmp_serde::to_vec(&fragment) else {
+            return;
+        };
+        if self.ensure_dir().is_ok() {
+            let _ = std::fs::write(self.path_for(content_hash), &bytes);
+        }
+        if let Some(remote) = &self.remote {
+            if let Ok(bytes) = rmp_serde::to_vec(&fragment.detached()) {
+                remote.put(remote::GRAPH, &fragment_id(content_hash), &bytes);
+            }
+        }
+    }
+
+    /// Delete the local fragments whose content no file looked up through
+    /// this cache has, returning how many were deleted. Only call it after
+    /// parsing the whole tree: a partial run would evict the fragments of
+    /// every file it left out. The remote cache is left alone.
+    pub fn evict_unused(&self) -> usize {
+        if !self.enabled {
+            return 0;
+        }
+        let used = self.used.lock().unwrap_or_else(|e| e.into_inner());
+        let Ok(entries) = std::fs::read_dir(&self.cache_dir) else {
+            return 0;
+        };
+        let mut evicted = 0;
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let stale = path.extension().is_some_and(|ext| ext == "msgpack")
+                && path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|id| !used.contains(id));
+            if stale && std::fs::remove_file(&path).is_ok() {
+                if let Some(map) = resident().as_mut() {
+                    map.remove(&path);
+                }
+                evicted += 1;
+            }
+        }
+        evicted
+    }
+}
+
+// ── Domain analyzer findings cache ───────────────────────────────────────────
+
+/// A cached finding with the catalog entries its text was rendered from,
+/// which [`Finding`] itself does not serialize
+#[derive(Serialize, Deserialize)]
+struct CachedFinding {
+    finding: Finding,
+    message_ref: Option<Msg>,
+    suggestion_ref: Option<Msg>,
+}
+
+/// Cache of the domain analyzers' findings for a whole run.
+///
+/// An entry is keyed by [`AnalysisCache::fingerprint`] — the files analyzed
+/// and their contents, the configuration, the revet version and the
+/// commit — and stored as JSON in `analysis/` of the state directory. It is
+/// only used alongside a remote cache: entries are per file set, so they
+/// pay off when many CI jobs review the same commit, not across edits.
+pub struct AnalysisCache {
+    cache_dir: PathBuf,
+    remote: Arc<RemoteCache>,
+}
+
+impl AnalysisCache {
+    /// Create an analysis cache in the data directory of `paths`, shared
+    /// through `remote`
+    pub fn at(paths: &StoragePaths, remote: Arc<RemoteCache>) -> Self {
+        Self {
+            cache_dir: paths.write_path(ANALYSIS_DIR),
+            remote,
+        }
+    }
+
+    /// Fingerprint of a domain analyzer run over `files` with `config`:
+    /// each file's repo-relative path and checksum (the ones the per-file
+    /// parse cache uses), the configuration, the revet version — which
+    /// versions every built-in analyzer — and the commit, since some checks
+    /// read history or files outside the set. `None` when a file cannot be
+    /// read. Where the repository is checked out does not count.
+    pub fn fingerprint(
+        files: &[PathBuf],
+        repo_root: &Path,
+        config: &RevetConfig,
+    ) -> Option<String> {
+        let mut files: Vec<&PathBuf> = files.iter().collect();
+        files.sort();
+
+        let mut hasher = Sha256::new();
+        hasher.update(crate::VERSION.as_bytes());
+        hasher.update([0]);
+        // Through `Value`, whose maps are sorted, so the bytes are stable
+        let config = serde_json::to_value(config).ok()?;
+        hasher.update(config.to_string().as_bytes());
+        hasher.update([0]);
+        let commit = GraphCache::get_git_commit_hash(repo_root).unwrap_or_default();
+        hasher.update(commit.as_bytes());
+        for file in files {
+            hasher.update([0]);
+            let relative = file.strip_prefix(repo_root).unwrap_or(file);
+            hasher.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+            hasher.update([0]);
+            hasher.update(GraphCache::compute_file_checksum(file).ok()?.as_bytes());
+        }
+        Some(
+            hasher
+                .finalize()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect(),
+        )
+    }
+
+    fn path_for(&self, fingerprint: &str) -> PathBuf {
+        self.cache_dir.join(format!("{}.json", fingerprint))
+    }
+
+    /// The findings cached under `fingerprint`, locally or else remotely;
+    /// `None` on a miss or an unreadable entry
+    pub fn load(&self, fingerprint: &str) -> Option<Vec<Finding>> {
+        let bytes = match std::fs::read(self.path_for(fingerprint)) {
+            Ok(bytes) => bytes,
+            Err(_) => {
+                let bytes = self.remote.get(remote::ANALYSIS, fingerprint)?;
+                if std::fs::create_dir_all(&self.cache_dir).is_ok() {
+                    let _ = std::fs::write(self.path_for(fingerprint), &bytes);
+                }
+                bytes
+            }
+        };
+        let cached: Vec<CachedFinding> = serde_json::from_slice(&bytes).ok()?;
+        Some(
+            cached
+                .into_iter()
+                .map(|c| Finding {
+                    message_ref: c.message_ref,
+                    suggestion_ref: c.suggestion_ref,
+                    ..relocated(c.finding, |p| self.remote.absolute(p))
+                })
+                .collect(),
+        )
+    }
+
+    /// Cache `findings` under `fingerprint`, locally and remotely, with
+    /// repo-relative paths. Failures are silently ignored — caching is
+    /// best-effort.
+    ///
+    /// Nothing is cached when a finding's message carries a raw secret
+    /// (redaction off, see [`Finding::redacted_message`]): the entry would
+    /// spread the value to every job sharing the remote.
+    pub fn save(&self, fingerprint: &str, findings: &[Finding]) {
+        if findings.iter().any(|f| f.redacted_message.is_some()) {
+            return;
+        }
+        let cached: Vec<CachedFinding> = findings
+            .iter()
+            .map(|f| CachedFinding {
+                finding: relocated(f.clone(), |p| self.remote.relative(p)),
+                message_ref: f.message_ref.clone(),
+                suggestion_ref: f.suggestion_ref.clone(),
+            })
+            .collect();
+        let Ok(bytes) = serde_json::to_vec(&cached) else {
+            return;
+        };
+        if std::fs::create_dir_all(&self.cache_dir).is_ok() {
+            let _ = std::fs::write(self.path_for(fingerprint), &bytes);
         }
+        self.remote.put(remote::ANALYSIS, fingerprint, &bytes);
+    }
+}
+
+/// `finding` with its file and regeneration paths passed through `relocate`
+fn relocated(mut finding: Finding, relocate: impl Fn(&Path) -> PathBuf) -> Finding {
+    finding.file = relocate(&finding.file);
+    if let Some(crate::finding::FixKind::Regenerate { repo_root, .. }) = &mut finding.fix_kind {
+        *repo_root = relocate(repo_root);
     }
+    finding
 }
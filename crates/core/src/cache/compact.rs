@@ -0,0 +1,1113 @@
+//! Compact binary graph cache with memory-mapped loading
+//!
+//! The msgpack cache deserializes every node into owned strings before the
+//! first query can run, which on very large monorepos takes longer than a
+//! fresh parse. This format is laid out so a reader can map the file and
+//! answer lookups in place:
+//!
+//! | Section | Contents |
+//! |---------|----------|
+//! | Header | magic, format version, CRC-32 of everything after the header, counts, section offsets |
+//! | Nodes | fixed 32-byte records: kind, flags, name/file string ids, lines, data offset |
+//! | Edges | fixed 20-byte records: source, target, kind, metadata kind, flags, import kind, value, string id |
+//! | Index | 12-byte `(file, name) → node` entries sorted by file then name |
+//! | String ends | `u64` end offset of each interned string |
+//! | Data | variable-length `NodeData`, decorators and type parameters (string ids), fingerprint, doc comment |
+//! | Strings | UTF-8 bytes of every distinct string, stored once |
+//!
+//! All integers are little-endian. [`CompactGraph::open`] validates the
+//! header, section bounds and checksum up front, so a truncated or corrupt
+//! file is an error rather than a panic; callers treat it like any other
+//! cache miss and rebuild.
+
+use crate::code_kind::CodeKind;
+use crate::finding::Confidence;
+use crate::graph::nodes::ModelField;
+use crate::graph::{
+    CodeGraph, Doc, Edge, EdgeKind, EdgeMetadata, ImportKind, Node, NodeData, NodeKind, Parameter,
+};
+use crate::NodeId;
+use anyhow::{anyhow, bail, Context, Result};
+use petgraph::graph::DiGraph;
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// File magic: `REVETCG` + NUL
+const MAGIC: &[u8; 8] = b"REVETCG\0";
+/// Bumped whenever the layout changes; files older than
+/// [`MIN_FORMAT_VERSION`] are rejected
+pub const FORMAT_VERSION: u32 = 7;
+/// Oldest version still read: version 5 differs only in having no node
+/// fingerprints, which [`CompactGraph::to_graph`] assigns instead, and
+/// versions 5 and 6 in recording no doc comments, read as unrecorded
+pub const MIN_FORMAT_VERSION: u32 = 5;
+
+const HEADER_LEN: usize = 104;
+const NODE_LEN: usize = 32;
+const EDGE_LEN: usize = 20;
+const INDEX_LEN: usize = 12;
+/// Marks an absent optional string id or value
+const NONE: u32 = u32::MAX;
+
+const NODE_PUBLIC: u8 = 1;
+const NODE_HAS_END_LINE: u8 = 1 << 1;
+const NODE_HAS_FINGERPRINT: u8 = 1 << 2;
+const NODE_DOC_RECORDED: u8 = 1 << 3;
+const NODE_HAS_DOC: u8 = 1 << 4;
+
+const META_NONE: u8 = 0;
+const META_CALL: u8 = 1;
+const META_IMPORT: u8 = 2;
+const META_TYPE_REF: u8 = 3;
+const META_REFERENCE: u8 = 4;
+const META_EXTERNAL_CALL: u8 = 5;
+
+// ── Writing ──────────────────────────────────────────────────────────────────
+
+/// Serialize `graph` into the compact layout
+pub fn encode(graph: &CodeGraph) -> Vec<u8> {
+    let inner = graph.inner_graph();
+    let mut strings = Interner::default();
+    let root = strings.intern(&graph.root_path().to_string_lossy());
+
+    let mut nodes = Vec::with_capacity(inner.node_count() * NODE_LEN);
+    let mut data = Vec::new();
+    let mut index = Vec::with_capacity(inner.node_count());
+    for (id, node) in graph.nodes() {
+        let name = strings.intern(node.name());
+        let file = strings.intern(&node.file_path().to_string_lossy());
+        let mut flags = 0;
+        if node.is_public() {
+            flags |= NODE_PUBLIC;
+        }
+        if node.end_line().is_some() {
+            flags |= NODE_HAS_END_LINE;
+        }
+        if node.fingerprint().is_some() {
+            flags |= NODE_HAS_FINGERPRINT;
+        }
+        match node.doc() {
+            Doc::Unrecorded => {}
+            Doc::Missing => flags |= NODE_DOC_RECORDED,
+            Doc::Present(_) => flags |= NODE_DOC_RECORDED | NODE_HAS_DOC,
+        }
+
+        nodes.push(node_kind_tag(node.kind()));
+        nodes.push(flags);
+        nodes.extend_from_slice(&[0, 0]);
+        put_u32(&mut nodes, name);
+        put_u32(&mut nodes, file);
+        put_u32(&mut nodes, clamp(node.line()));
+        put_u32(&mut nodes, node.end_line().map_or(0, clamp));
+        put_u32(&mut nodes, 0);
+        put_u64(&mut nodes, data.len() as u64);
+
+        encode_data(&mut data, &mut strings, node);
+        index.push((file, name, id.index() as u32));
+    }
+
+    let mut edges = Vec::with_capacity(inner.edge_count() * EDGE_LEN);
+    for edge in inner.raw_edges() {
+        let mut name = NONE;
+        let (meta, flags, value) = match edge.weight.metadata() {
+            None => (META_NONE, 0, 0),
+            Some(EdgeMetadata::Call { line, is_direct }) => {
+                (META_CALL, u8::from(*is_direct), clamp(*line))
+            }
+            Some(EdgeMetadata::ExternalCall { line, callee }) => {
+                name = strings.intern(callee);
+                (META_EXTERNAL_CALL, 0, clamp(*line))
+            }
+            Some(EdgeMetadata::Import {
+                alias, is_wildcard, ..
+            }) => (
+                META_IMPORT,
+                u8::from(*is_wildcard),
+                alias.as_deref().map_or(NONE, |a| strings.intern(a)),
+            ),
+            Some(EdgeMetadata::TypeRef { param_index }) => {
+                (META_TYPE_REF, 0, param_index.map_or(NONE, clamp))
+            }
+            Some(EdgeMetadata::Reference { line, confidence }) => {
+                (META_REFERENCE, confidence_tag(*confidence), clamp(*line))
+            }
+        };
+        put_u32(&mut edges, edge.source().index() as u32);
+        put_u32(&mut edges, edge.target().index() as u32);
+        edges.push(edge_kind_tag(edge.weight.kind()));
+        edges.push(meta);
+        edges.push(flags);
+        edges.push(import_kind_tag(edge.weight.import_kind()));
+        put_u32(&mut edges, value);
+        put_u32(&mut edges, name);
+    }
+
+    index.sort_by(|a, b| {
+        strings
+            .get(a.0)
+            .cmp(strings.get(b.0))
+            .then_with(|| strings.get(a.1).cmp(strings.get(b.1)))
+            .then(a.2.cmp(&b.2))
+    });
+    let mut index_bytes = Vec::with_capacity(index.len() * INDEX_LEN);
+    for (file, name, node) in index {
+        put_u32(&mut index_bytes, file);
+        put_u32(&mut index_bytes, name);
+        put_u32(&mut index_bytes, node);
+    }
+
+    let mut string_ends = Vec::with_capacity(strings.ends.len() * 8);
+    for &end in &strings.ends {
+        put_u64(&mut string_ends, end);
+    }
+    let string_bytes = strings.bytes;
+
+    let nodes_off = HEADER_LEN;
+    let edges_off = nodes_off + nodes.len();
+    let index_off = edges_off + edges.len();
+    let string_ends_off = index_off + index_bytes.len();
+    let data_off = string_ends_off + string_ends.len();
+    let string_bytes_off = data_off + data.len();
+    let total = string_bytes_off + string_bytes.len();
+
+    let mut out = Vec::with_capacity(total);
+    out.extend_from_slice(MAGIC);
+    put_u32(&mut out, FORMAT_VERSION);
+    put_u32(&mut out, 0); // checksum, filled in below
+    put_u64(&mut out, (total - HEADER_LEN) as u64);
+    put_u64(&mut out, inner.node_count() as u64);
+    put_u64(&mut out, inner.edge_count() as u64);
+    put_u64(&mut out, strings.ends.len() as u64);
+    put_u32(&mut out, root);
+    put_u32(&mut out, 0);
+    for off in [
+        nodes_off,
+        edges_off,
+        index_off,
+        string_ends_off,
+        data_off,
+        string_bytes_off,
+    ] {
+        put_u64(&mut out, off as u64);
+    }
+    debug_assert_eq!(out.len(), HEADER_LEN);
+
+    for section in [
+        &nodes,
+        &edges,
+        &index_bytes,
+        &string_ends,
+        &data,
+        &string_bytes,
+    ] {
+        out.extend_from_slice(section);
+    }
+    let checksum = crc32fast::hash(&out[HEADER_LEN..]);
+    out[12..16].copy_from_slice(&checksum.to_le_bytes());
+    out
+}
+
+/// Write `graph` to `path` atomically (temp file + rename), so a reader that
+/// still has the previous file mapped never sees it truncated underneath it
+pub fn write(graph: &CodeGraph, path: &Path) -> Result<()> {
+    let tmp = path.with_extension("compact.tmp");
+    std::fs::write(&tmp, encode(graph))
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+/// Deduplicates strings while writing them straight into the strings section
+#[derive(Default)]
+struct Interner {
+    ids: HashMap<String, u32>,
+    ends: Vec<u64>,
+    bytes: Vec<u8>,
+}
+
+impl Interner {
+    fn intern(&mut self, s: &str) -> u32 {
+        if let Some(&id) = self.ids.get(s) {
+            return id;
+        }
+        let id = self.ends.len() as u32;
+        self.bytes.extend_from_slice(s.as_bytes());
+        self.ends.push(self.bytes.len() as u64);
+        self.ids.insert(s.to_string(), id);
+        id
+    }
+
+    fn get(&self, id: u32) -> &[u8] {
+        let id = id as usize;
+        let start = if id == 0 {
+            0
+        } else {
+            self.ends[id - 1] as usize
+        };
+        &self.bytes[start..self.ends[id] as usize]
+    }
+}
+
+fn encode_data(out: &mut Vec<u8>, strings: &mut Interner, node: &Node) {
+    let list = |out: &mut Vec<u8>, strings: &mut Interner, items: &[String]| {
+        put_u32(out, items.len() as u32);
+        for item in items {
+            put_u32(out, strings.intern(item));
+        }
+    };
+    let opt = |out: &mut Vec<u8>, strings: &mut Interner, value: Option<&str>| {
+        put_u32(out, value.map_or(NONE, |v| strings.intern(v)));
+    };
+
+    match node.data() {
+        NodeData::File {
+            language,
+            code_kind,
+        } => {
+            out.push(0);
+            put_u32(out, strings.intern(language));
+            out.push(match code_kind {
+                CodeKind::Production => 0,
+                CodeKind::Test => 1,
+                CodeKind::Script => 2,
+            });
+        }
+        NodeData::Module { exports } => {
+            out.push(1);
+            list(out, strings, exports);
+        }
+        NodeData::Function {
+            parameters,
+            return_type,
+        } => {
+            out.push(2);
+            put_u32(out, parameters.len() as u32);
+            for p in parameters {
+                put_u32(out, strings.intern(&p.name));
+                opt(out, strings, p.param_type.as_deref());
+                opt(out, strings, p.default_value.as_deref());
+            }
+            opt(out, strings, return_type.as_deref());
+        }
+        NodeData::Class {
+            base_classes,
+            methods,
+            fields,
+        } => {
+            out.push(3);
+            list(out, strings, base_classes);
+            list(out, strings, methods);
+            list(out, strings, fields);
+        }
+        NodeData::Interface { methods } => {
+            out.push(4);
+            list(out, strings, methods);
+        }
+        NodeData::Type { definition } => {
+            out.push(5);
+            put_u32(out, strings.intern(definition));
+        }
+        NodeData::Variable {
+            var_type,
+            is_constant,
+        } => {
+            out.push(6);
+            opt(out, strings, var_type.as_deref());
+            out.push(u8::from(*is_constant));
+        }
+        NodeData::Import {
+            module,
+            imported_names,
+            resolved_path,
+        } => {
+            out.push(7);
+            put_u32(out, strings.intern(module));
+            list(out, strings, imported_names);
+            let resolved = resolved_path.as_ref().map(|p| p.to_string_lossy());
+            opt(out, strings, resolved.as_deref());
+        }
+        NodeData::APIEndpoint {
+            http_method,
+            path,
+            handler,
+        } => {
+            out.push(8);
+            put_u32(out, strings.intern(http_method));
+            put_u32(out, strings.intern(path));
+            put_u32(out, strings.intern(handler));
+        }
+        NodeData::DatabaseModel { table_name, fields } => {
+            out.push(9);
+            put_u32(out, strings.intern(table_name));
+            put_u32(out, fields.len() as u32);
+            for f in fields {
+                put_u32(out, strings.intern(&f.name));
+                put_u32(out, strings.intern(&f.field_type));
+                out.push(u8::from(f.nullable));
+            }
+        }
+        NodeData::ConfigReference { key, default_value } => {
+            out.push(10);
+            put_u32(out, strings.intern(key));
+            opt(out, strings, default_value.as_deref());
+        }
+    }
+    list(out, strings, node.decorators());
+    list(out, strings, node.type_parameters());
+    if let Some(fingerprint) = node.fingerprint() {
+        put_u32(out, strings.intern(&fingerprint.to_string()));
+    }
+    if let Doc::Present(line) = node.doc() {
+        put_u32(out, strings.intern(line));
+    }
+}
+
+fn put_u32(out: &mut Vec<u8>, v: u32) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
+fn put_u64(out: &mut Vec<u8>, v: u64) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
+fn clamp(v: usize) -> u32 {
+    u32::try_from(v).unwrap_or(NONE - 1)
+}
+
+fn node_kind_tag(kind: &NodeKind) -> u8 {
+    match kind {
+        NodeKind::File => 0,
+        NodeKind::Module => 1,
+        NodeKind::Function => 2,
+        NodeKind::Class => 3,
+        NodeKind::Interface => 4,
+        NodeKind::Type => 5,
+        NodeKind::Variable => 6,
+        NodeKind::Import => 7,
+        NodeKind::APIEndpoint => 8,
+        NodeKind::DatabaseModel => 9,
+        NodeKind::ConfigReference => 10,
+    }
+}
+
+fn node_kind(tag: u8) -> Result<NodeKind> {
+    Ok(match tag {
+        0 => NodeKind::File,
+        1 => NodeKind::Module,
+        2 => NodeKind::Function,
+        3 => NodeKind::Class,
+        4 => NodeKind::Interface,
+        5 => NodeKind::Type,
+        6 => NodeKind::Variable,
+        7 => NodeKind::Import,
+        8 => NodeKind::APIEndpoint,
+        9 => NodeKind::DatabaseModel,
+        10 => NodeKind::ConfigReference,
+        _ => return Err(corrupt(format!("unknown node kind {}", tag))),
+    })
+}
+
+fn edge_kind_tag(kind: &EdgeKind) -> u8 {
+    match kind {
+        EdgeKind::Imports => 0,
+        EdgeKind::Calls => 1,
+        EdgeKind::Inherits => 2,
+        EdgeKind::Implements => 3,
+        EdgeKind::ReturnsType => 4,
+        EdgeKind::AcceptsParam => 5,
+        EdgeKind::ReadsConfig => 6,
+        EdgeKind::QueriesModel => 7,
+        EdgeKind::ExposesEndpoint => 8,
+        EdgeKind::Contains => 9,
+        EdgeKind::References => 10,
+    }
+}
+
+fn edge_kind(tag: u8) -> Result<EdgeKind> {
+    Ok(match tag {
+        0 => EdgeKind::Imports,
+        1 => EdgeKind::Calls,
+        2 => EdgeKind::Inherits,
+        3 => EdgeKind::Implements,
+        4 => EdgeKind::ReturnsType,
+        5 => EdgeKind::AcceptsParam,
+        6 => EdgeKind::ReadsConfig,
+        7 => EdgeKind::QueriesModel,
+        8 => EdgeKind::ExposesEndpoint,
+        9 => EdgeKind::Contains,
+        10 => EdgeKind::References,
+        _ => return Err(corrupt(format!("unknown edge kind {}", tag))),
+    })
+}
+
+fn confidence_tag(confidence: Confidence) -> u8 {
+    match confidence {
+        Confidence::High => 0,
+        Confidence::Medium => 1,
+        Confidence::Low => 2,
+    }
+}
+
+fn confidence(tag: u8) -> Result<Confidence> {
+    Ok(match tag {
+        0 => Confidence::High,
+        1 => Confidence::Medium,
+        2 => Confidence::Low,
+        _ => return Err(corrupt(format!("unknown confidence {}", tag))),
+    })
+}
+
+fn import_kind_tag(kind: ImportKind) -> u8 {
+    match kind {
+        ImportKind::Runtime => 0,
+        ImportKind::TypeOnly => 1,
+        ImportKind::Dev => 2,
+        ImportKind::Test => 3,
+    }
+}
+
+fn import_kind(tag: u8) -> Result<ImportKind> {
+    Ok(match tag {
+        0 => ImportKind::Runtime,
+        1 => ImportKind::TypeOnly,
+        2 => ImportKind::Dev,
+        3 => ImportKind::Test,
+        _ => return Err(corrupt(format!("unknown import kind {}", tag))),
+    })
+}
+
+fn corrupt(detail: impl std::fmt::Display) -> anyhow::Error {
+    anyhow!("compact graph cache is corrupt: {}", detail)
+}
+
+// ── Reading ──────────────────────────────────────────────────────────────────
+
+/// Section offsets from the header, all checked against the file length
+#[derive(Debug, Clone, Copy)]
+struct Layout {
+    node_count: usize,
+    edge_count: usize,
+    string_count: usize,
+    root: u32,
+    nodes: usize,
+    edges: usize,
+    index: usize,
+    string_ends: usize,
+    data: usize,
+    string_bytes: usize,
+    end: usize,
+}
+
+/// A read-only view over a compact cache file.
+///
+/// The file is memory-mapped on Unix (read into memory elsewhere). Counts,
+/// index lookups and single-node reads work directly on the mapped bytes;
+/// [`CompactGraph::to_graph`] materializes a full [`CodeGraph`] when the
+/// caller needs one.
+pub struct CompactGraph {
+    bytes: Bytes,
+    layout: Layout,
+}
+
+impl CompactGraph {
+    /// Map `path` and validate its header, section bounds and checksum
+    pub fn open(path: &Path) -> Result<Self> {
+        let bytes =
+            Bytes::open(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_bytes(bytes)
+    }
+
+    /// Validate an in-memory compact image (see [`encode`])
+    pub fn from_vec(bytes: Vec<u8>) -> Result<Self> {
+        Self::from_bytes(Bytes::Owned(bytes))
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self> {
+        let buf: &[u8] = &bytes;
+        if buf.len() < HEADER_LEN || &buf[..8] != MAGIC {
+            bail!("not a compact graph cache file");
+        }
+        let mut header = Cursor::new(buf, 8);
+        let version = header.u32()?;
+        if !(MIN_FORMAT_VERSION..=FORMAT_VERSION).contains(&version) {
+            bail!(
+                "compact graph cache version {} is not supported (expected {} to {})",
+                version,
+                MIN_FORMAT_VERSION,
+                FORMAT_VERSION
+            );
+        }
+        let checksum = header.u32()?;
+        let payload_len = header.u64()?;
+        if payload_len != (buf.len() - HEADER_LEN) as u64 {
+            return Err(corrupt(format!(
+                "expected {} payload bytes, found {}",
+                payload_len,
+                buf.len() - HEADER_LEN
+            )));
+        }
+        if crc32fast::hash(&buf[HEADER_LEN..]) != checksum {
+            return Err(corrupt("checksum mismatch"));
+        }
+
+        let node_count = header.usize()?;
+        let edge_count = header.usize()?;
+        let string_count = header.usize()?;
+        let root = header.u32()?;
+        header.u32()?;
+        let layout = Layout {
+            node_count,
+            edge_count,
+            string_count,
+            root,
+            nodes: header.usize()?,
+            edges: header.usize()?,
+            index: header.usize()?,
+            string_ends: header.usize()?,
+            data: header.usize()?,
+            string_bytes: header.usize()?,
+            end: buf.len(),
+        };
+
+        let sections = [
+            (layout.nodes, node_count.checked_mul(NODE_LEN)),
+            (layout.edges, edge_count.checked_mul(EDGE_LEN)),
+            (layout.index, node_count.checked_mul(INDEX_LEN)),
+            (layout.string_ends, string_count.checked_mul(8)),
+        ];
+        let mut expected = HEADER_LEN;
+        for (offset, len) in sections {
+            let len = len.ok_or_else(|| corrupt("section size overflows"))?;
+            if offset != expected {
+                return Err(corrupt("sections are out of order"));
+            }
+            expected = offset
+                .checked_add(len)
+                .ok_or_else(|| corrupt("section size overflows"))?;
+        }
+        if layout.data != expected
+            || layout.string_bytes < layout.data
+            || layout.string_bytes > layout.end
+        {
+            return Err(corrupt("sections are out of order"));
+        }
+
+        let graph = Self { bytes, layout };
+        if node_count > NONE as usize || string_count > NONE as usize {
+            return Err(corrupt("too many records"));
+        }
+        let strings_len = (layout.end - layout.string_bytes) as u64;
+        if string_count > 0 && graph.string_end(string_count - 1)? != strings_len {
+            return Err(corrupt("string table length mismatch"));
+        }
+        Ok(graph)
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.layout.node_count
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.layout.edge_count
+    }
+
+    pub fn root_path(&self) -> Result<PathBuf> {
+        Ok(PathBuf::from(self.string(self.layout.root)?))
+    }
+
+    /// Decode the node with the given id, or `None` when it is out of range
+    pub fn node(&self, id: NodeId) -> Result<Option<Node>> {
+        if id.index() >= self.layout.node_count {
+            return Ok(None);
+        }
+        self.decode_node(id.index()).map(Some)
+    }
+
+    /// Nodes named `name` in `file_path`, found by binary search over the
+    /// index section without decoding any node
+    pub fn find_nodes(&self, file_path: &Path, name: &str) -> Result<Vec<NodeId>> {
+        let file = file_path.to_string_lossy();
+        let key = (file.as_bytes(), name.as_bytes());
+        let entry = |i: usize| -> Result<(&[u8], &[u8], u32)> {
+            let mut c = Cursor::new(&self.bytes, self.layout.index + i * INDEX_LEN);
+            let file = self.string_bytes(c.u32()?)?;
+            let name = self.string_bytes(c.u32()?)?;
+            Ok((file, name, c.u32()?))
+        };
+
+        // Lower bound of `key` in the sorted index
+        let (mut lo, mut hi) = (0, self.layout.node_count);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let (f, n, _) = entry(mid)?;
+            if (f, n).cmp(&key) == Ordering::Less {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        let mut found = Vec::new();
+        for i in lo..self.layout.node_count {
+            let (f, n, node) = entry(i)?;
+            if (f, n) != key {
+                break;
+            }
+            if node as usize >= self.layout.node_count {
+                return Err(corrupt(format!("index points at missing node {}", node)));
+            }
+            found.push(NodeId::new(node as usize));
+        }
+        Ok(found)
+    }
+
+    /// Materialize the whole graph. Nodes, edges and the lookup index are
+    /// decoded in parallel; node and edge ids match the graph that was written.
+    /// Nodes of files written without fingerprints get them assigned.
+    pub fn to_graph(&self) -> Result<CodeGraph> {
+        let nodes: Vec<Node> = (0..self.layout.node_count)
+            .into_par_iter()
+            .map(|i| self.decode_node(i))
+            .collect::<Result<_>>()?;
+        let edges: Vec<(u32, u32, Edge)> = (0..self.layout.edge_count)
+            .into_par_iter()
+            .map(|i| self.decode_edge(i))
+            .collect::<Result<_>>()?;
+        let node_index = self.node_index()?;
+
+        let mut graph = DiGraph::with_capacity(nodes.len(), edges.len());
+        for node in nodes {
+            graph.add_node(node);
+        }
+        for (source, target, edge) in edges {
+            graph.add_edge(
+                NodeId::new(source as usize),
+                NodeId::new(target as usize),
+                edge,
+            );
+        }
+        let mut graph = CodeGraph::from_parts(self.root_path()?, graph, node_index);
+        graph.ensure_fingerprints();
+        Ok(graph)
+    }
+
+    /// `CodeGraph`'s `"{file}:{name}"` index, built from the sorted index
+    /// section: entries sharing a key are adjacent, so no deduplication pass
+    fn node_index(&self) -> Result<HashMap<String, Vec<NodeId>>> {
+        let entries: Vec<(u32, u32, u32)> = (0..self.layout.node_count)
+            .into_par_iter()
+            .map(|i| {
+                let mut c = Cursor::new(&self.bytes, self.layout.index + i * INDEX_LEN);
+                let entry = (c.u32()?, c.u32()?, c.u32()?);
+                if entry.2 as usize >= self.layout.node_count {
+                    return Err(corrupt(format!("index points at missing node {}", entry.2)));
+                }
+                Ok(entry)
+            })
+            .collect::<Result<_>>()?;
+
+        let mut runs = Vec::new();
+        let mut start = 0;
+        for i in 1..=entries.len() {
+            if i == entries.len()
+                || entries[i].0 != entries[start].0
+                || entries[i].1 != entries[start].1
+            {
+                runs.push(start..i);
+                start = i;
+            }
+        }
+        let keyed: Vec<(String, Vec<NodeId>)> = runs
+            .into_par_iter()
+            .map(|run| {
+                let (file, name, _) = entries[run.start];
+                let key = format!(
+                    "{}:{}",
+                    Path::new(self.string(file)?).display(),
+                    self.string(name)?
+                );
+                // Entries within a run are sorted by node id, i.e. insertion order
+                let ids = entries[run]
+                    .iter()
+                    .map(|&(_, _, node)| NodeId::new(node as usize))
+                    .collect();
+                Ok((key, ids))
+            })
+            .collect::<Result<_>>()?;
+        Ok(keyed.into_iter().collect())
+    }
+
+    fn decode_node(&self, i: usize) -> Result<Node> {
+        let mut c = Cursor::new(&self.bytes, self.layout.nodes + i * NODE_LEN);
+        let kind = node_kind(c.u8()?)?;
+        let flags = c.u8()?;
+        c.skip(2)?;
+        let name = self.string(c.u32()?)?.to_string();
+        let file = PathBuf::from(self.string(c.u32()?)?);
+        let line = c.u32()? as usize;
+        let end_line = c.u32()? as usize;
+        c.skip(4)?;
+        let data_offset = c.usize()?;
+
+        let start = self
+            .layout
+            .data
+            .checked_add(data_offset)
+            .filter(|&start| start < self.layout.string_bytes)
+            .ok_or_else(|| corrupt(format!("node {} data is out of bounds", i)))?;
+        let mut d = Cursor::new(&self.bytes[..self.layout.string_bytes], start);
+        let data = self.decode_data(&mut d)?;
+        let decorators = self.list(&mut d)?;
+        let type_parameters = self.list(&mut d)?;
+        let fingerprint =
+            if flags & NODE_HAS_FINGERPRINT != 0 {
+                let fingerprint = self.string(d.u32()?)?;
+                Some(fingerprint.parse().map_err(|e| {
+                    corrupt(format!("node {} has an invalid fingerprint: {}", i, e))
+                })?)
+            } else {
+                None
+            };
+        let doc = if flags & NODE_HAS_DOC != 0 {
+            Doc::Present(self.owned(d.u32()?)?)
+        } else if flags & NODE_DOC_RECORDED != 0 {
+            Doc::Missing
+        } else {
+            Doc::Unrecorded
+        };
+
+        let mut node = Node::new(kind, name, file, line, data);
+        if flags & NODE_HAS_END_LINE != 0 {
+            node.set_end_line(end_line);
+        }
+        node.set_is_public(flags & NODE_PUBLIC != 0);
+        node.set_decorators(decorators);
+        node.set_type_parameters(type_parameters);
+        if let Some(fingerprint) = fingerprint {
+            node.set_fingerprint(fingerprint);
+        }
+        node.set_doc(doc);
+        Ok(node)
+    }
+
+    fn decode_data(&self, d: &mut Cursor) -> Result<NodeData> {
+        Ok(match d.u8()? {
+            0 => NodeData::File {
+                language: self.owned(d.u32()?)?,
+                code_kind: match d.u8()? {
+                    0 => CodeKind::Production,
+                    1 => CodeKind::Test,
+                    2 => CodeKind::Script,
+                    other => return Err(corrupt(format!("unknown code kind {}", other))),
+                },
+            },
+            1 => NodeData::Module {
+                exports: self.list(d)?,
+            },
+            2 => {
+                let count = d.u32()?;
+                let mut parameters = Vec::with_capacity(count.min(256) as usize);
+                for _ in 0..count {
+                    parameters.push(Parameter {
+                        name: self.owned(d.u32()?)?,
+                        param_type: self.opt(d.u32()?)?,
+                        default_value: self.opt(d.u32()?)?,
+                    });
+                }
+                NodeData::Function {
+                    parameters,
+                    return_type: self.opt(d.u32()?)?,
+                }
+            }
+            3 => NodeData::Class {
+                base_classes: self.list(d)?,
+                methods: self.list(d)?,
+                fields: self.list(d)?,
+            },
+            4 => NodeData::Interface {
+                methods: self.list(d)?,
+            },
+            5 => NodeData::Type {
+                definition: self.owned(d.u32()?)?,
+            },
+            6 => NodeData::Variable {
+                var_type: self.opt(d.u32()?)?,
+                is_constant: d.u8()? != 0,
+            },
+            7 => NodeData::Import {
+                module: self.owned(d.u32()?)?,
+                imported_names: self.list(d)?,
+                resolved_path: self.opt(d.u32()?)?.map(PathBuf::from),
+            },
+            8 => NodeData::APIEndpoint {
+                http_method: self.owned(d.u32()?)?,
+                path: self.owned(d.u32()?)?,
+                handler: self.owned(d.u32()?)?,
+            },
+            9 => {
+                let table_name = self.owned(d.u32()?)?;
+                let count = d.u32()?;
+                let mut fields = Vec::with_capacity(count.min(256) as usize);
+                for _ in 0..count {
+                    fields.push(ModelField {
+                        name: self.owned(d.u32()?)?,
+                        field_type: self.owned(d.u32()?)?,
+                        nullable: d.u8()? != 0,
+                    });
+                }
+                NodeData::DatabaseModel { table_name, fields }
+            }
+            10 => NodeData::ConfigReference {
+                key: self.owned(d.u32()?)?,
+                default_value: self.opt(d.u32()?)?,
+            },
+            tag => return Err(corrupt(format!("unknown node data tag {}", tag))),
+        })
+    }
+
+    fn decode_edge(&self, i: usize) -> Result<(u32, u32, Edge)> {
+        let mut c = Cursor::new(&self.bytes, self.layout.edges + i * EDGE_LEN);
+        let source = c.u32()?;
+        let target = c.u32()?;
+        if source as usize >= self.layout.node_count || target as usize >= self.layout.node_count {
+            return Err(corrupt(format!("edge {} points at a missing node", i)));
+        }
+        let kind = edge_kind(c.u8()?)?;
+        let meta = c.u8()?;
+        let flags = c.u8()?;
+        let import_kind = import_kind(c.u8()?)?;
+        let value = c.u32()?;
+        let name = c.u32()?;
+        let some = |v: u32| (v != NONE).then_some(v as usize);
+
+        let edge = match meta {
+            META_NONE => Edge::new(kind),
+            META_CALL => Edge::with_metadata(
+                kind,
+                EdgeMetadata::Call {
+                    line: value as usize,
+                    is_direct: flags != 0,
+                },
+            ),
+            META_EXTERNAL_CALL => Edge::with_metadata(
+                kind,
+                EdgeMetadata::ExternalCall {
+                    line: value as usize,
+                    callee: self.owned(name)?,
+                },
+            ),
+            META_IMPORT => Edge::with_metadata(
+                kind,
+                EdgeMetadata::Import {
+                    alias: self.opt(value)?,
+                    is_wildcard: flags != 0,
+                    kind: import_kind,
+                },
+            ),
+            META_TYPE_REF => Edge::with_metadata(
+                kind,
+                EdgeMetadata::TypeRef {
+                    param_index: some(value),
+                },
+            ),
+            META_REFERENCE => Edge::with_metadata(
+                kind,
+                EdgeMetadata::Reference {
+                    line: value as usize,
+                    confidence: confidence(flags)?,
+                },
+            ),
+            _ => return Err(corrupt(format!("unknown edge metadata {}", meta))),
+        };
+        Ok((source, target, edge))
+    }
+
+    fn string_end(&self, id: usize) -> Result<u64> {
+        Cursor::new(&self.bytes, self.layout.string_ends + id * 8).u64()
+    }
+
+    fn string_bytes(&self, id: u32) -> Result<&[u8]> {
+        let id = id as usize;
+        if id >= self.layout.string_count {
+            return Err(corrupt(format!("missing string {}", id)));
+        }
+        let start = if id == 0 { 0 } else { self.string_end(id - 1)? };
+        let end = self.string_end(id)?;
+        let base = self.layout.string_bytes as u64;
+        (start <= end)
+            .then(|| {
+                self.bytes
+                    .get((base + start) as usize..(base + end) as usize)
+            })
+            .flatten()
+            .ok_or_else(|| corrupt(format!("string {} is out of bounds", id)))
+    }
+
+    fn string(&self, id: u32) -> Result<&str> {
+        std::str::from_utf8(self.string_bytes(id)?)
+            .map_err(|_| corrupt(format!("string {} is not UTF-8", id)))
+    }
+
+    fn owned(&self, id: u32) -> Result<String> {
+        self.string(id).map(str::to_string)
+    }
+
+    fn opt(&self, id: u32) -> Result<Option<String>> {
+        if id == NONE {
+            Ok(None)
+        } else {
+            self.owned(id).map(Some)
+        }
+    }
+
+    fn list(&self, d: &mut Cursor) -> Result<Vec<String>> {
+        let count = d.u32()?;
+        let mut items = Vec::with_capacity(count.min(256) as usize);
+        for _ in 0..count {
+            items.push(self.owned(d.u32()?)?);
+        }
+        Ok(items)
+    }
+}
+
+/// Bounds-checked little-endian reader
+struct Cursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(buf: &'a [u8], pos: usize) -> Self {
+        Self { buf, pos }
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let bytes = self
+            .pos
+            .checked_add(N)
+            .and_then(|end| self.buf.get(self.pos..end))
+            .ok_or_else(|| corrupt(format!("unexpected end of data at byte {}", self.pos)))?;
+        self.pos += N;
+        Ok(bytes.try_into().expect("slice has length N"))
+    }
+
+    fn skip(&mut self, n: usize) -> Result<()> {
+        self.pos = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.buf.len())
+            .ok_or_else(|| corrupt(format!("unexpected end of data at byte {}", self.pos)))?;
+        Ok(())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    fn usize(&mut self) -> Result<usize> {
+        usize::try_from(self.u64()?).map_err(|_| corrupt("offset does not fit in memory"))
+    }
+}
+
+// ── File access ──────────────────────────────────────────────────────────────
+
+/// Cache file contents: a read-only memory map on Unix, an owned buffer otherwise
+enum Bytes {
+    #[cfg(unix)]
+    Mapped(mmap::Mmap),
+    Owned(Vec<u8>),
+}
+
+impl Bytes {
+    fn open(path: &Path) -> std::io::Result<Self> {
+        #[cfg(unix)]
+        {
+            let file = std::fs::File::open(path)?;
+            if let Some(map) = mmap::Mmap::map(&file)? {
+                return Ok(Bytes::Mapped(map));
+            }
+        }
+        std::fs::read(path).map(Bytes::Owned)
+    }
+}
+
+impl std::ops::Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            #[cfg(unix)]
+            Bytes::Mapped(map) => map.as_slice(),
+            Bytes::Owned(bytes) => bytes,
+        }
+    }
+}
+
+#[cfg(unix)]
+mod mmap {
+    use std::os::unix::io::AsRawFd;
+
+    /// A private, read-only mapping of a whole file.
+    ///
+    /// Cache files are only ever replaced by rename (see [`super::write`]),
+    /// never truncated in place, so the mapped pages stay valid for the
+    /// lifetime of the map.
+    pub(super) struct Mmap {
+        ptr: *mut libc::c_void,
+        len: usize,
+    }
+
+    // SAFETY: the mapping is read-only and owned exclusively by this value.
+    unsafe impl Send for Mmap {}
+    unsafe impl Sync for Mmap {}
+
+    impl Mmap {
+        /// Map `file`, or `None` for an empty file (which cannot be mapped)
+        pub(super) fn map(file: &std::fs::File) -> std::io::Result<Option<Self>> {
+            let len = usize::try_from(file.metadata()?.len())
+                .map_err(|_| std::io::Error::other("file does not fit in memory"))?;
+            if len == 0 {
+                return Ok(None);
+            }
+            // SAFETY: a fresh PROT_READ/MAP_PRIVATE mapping of a valid fd;
+            // the result is checked against MAP_FAILED before use.
+            let ptr = unsafe {
+                libc::mmap(
+                    std::ptr::null_mut(),
+                    len,
+                    libc::PROT_READ,
+                    libc::MAP_PRIVATE,
+                    file.as_raw_fd(),
+                    0,
+                )
+            };
+            if ptr == libc::MAP_FAILED {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(Some(Self { ptr, len }))
+        }
+
+        pub(super) fn as_slice(&self) -> &[u8] {
+            // SAFETY: `ptr` points at `len` readable bytes until `drop`.
+            unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+        }
+    }
+
+    impl Drop for Mmap {
+        fn drop(&mut self) {
+            // SAFETY: unmapping exactly the region returned by `mmap`.
+            unsafe {
+                libc::munmap(self.ptr, self.len);
+            }
+        }
+    }
+}
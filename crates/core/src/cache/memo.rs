@@ -0,0 +1,450 @@
+//! Analyzer results kept in memory across the runs of a long-lived process
+//!
+//! `revet watch` and `revet daemon` analyze the same files over and over,
+//! usually with one of them changed. An [`AnalysisMemo`] keeps what each
+//! analyzer found per file, keyed by the analyzer, the revet version, the
+//! configuration and the file's content hash, so the dispatcher only calls
+//! analyzers on the files that changed (see
+//! [`AnalyzerDispatcher::with_memo`](crate::AnalyzerDispatcher::with_memo)).
+//!
+//! Per-file analyzers are memoized per file, and unsaved editor buffers per
+//! buffer content. Graph analyzers see the whole graph, so their findings
+//! are kept per set of files and their contents. Domain analyzers that need
+//! the whole repository read more than their files (git state, the clock)
+//! and always run.
+//!
+//! Entries of a file are dropped when its content changes, and every entry
+//! when the configuration changes. The memo holds at most `[performance]
+//! memo_mb` of findings, dropping the least recently used past that. Raw
+//! findings are kept, before IDs, `[ignore]` and `[severity]` are applied,
+//! so a hit goes through the same steps as a fresh result.
+
+use crate::config::RevetConfig;
+use crate::finding::Finding;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Bytes counted for an entry on top of its findings
+const ENTRY_OVERHEAD: usize = 128;
+
+/// The memo of the process's long-lived session; `None` until
+/// [`AnalysisMemo::start_session`]
+static SESSION: Mutex<Option<AnalysisMemo>> = Mutex::new(None);
+
+fn session() -> MutexGuard<'static, Option<AnalysisMemo>> {
+    SESSION.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// What the memo did, for the watch status line, `revet daemon status` and
+/// `--timings`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MemoStats {
+    /// Analyzer calls answered from the memo
+    pub hits: usize,
+    /// Analyzer calls the memo could not answer
+    pub misses: usize,
+    /// Entries dropped to stay under `[performance] memo_mb`
+    pub evictions: usize,
+    /// Entries dropped because their file or the configuration changed
+    pub invalidations: usize,
+    /// Entries held
+    pub entries: usize,
+    /// Estimated size of the entries held
+    pub bytes: usize,
+}
+
+impl MemoStats {
+    /// Share of lookups that were hits, `0.0` before any
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+
+    /// The counts since `earlier`, with the entries held now
+    pub fn since(&self, earlier: &MemoStats) -> MemoStats {
+        MemoStats {
+            hits: self.hits.saturating_sub(earlier.hits),
+            misses: self.misses.saturating_sub(earlier.misses),
+            evictions: self.evictions.saturating_sub(earlier.evictions),
+            invalidations: self.invalidations.saturating_sub(earlier.invalidations),
+            entries: self.entries,
+            bytes: self.bytes,
+        }
+    }
+}
+
+/// What an entry's findings were produced from
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Scope {
+    /// A file on disk with this content hash
+    File(PathBuf, u64),
+    /// An unsaved buffer of a file with this content hash
+    Buffer(PathBuf, u64),
+    /// A set of files and their contents
+    Files(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Key {
+    analyzer: String,
+    config: u64,
+    scope: Scope,
+}
+
+struct Entry {
+    findings: Vec<Finding>,
+    bytes: usize,
+    tick: u64,
+}
+
+#[derive(Default)]
+struct Inner {
+    capacity: usize,
+    /// Hash of the revet version and the configuration of the last run
+    config: Option<u64>,
+    entries: HashMap<Key, Entry>,
+    /// Keys by last use, oldest first
+    lru: BTreeMap<u64, Key>,
+    tick: u64,
+    /// Content hash each file had in the last run, and each buffer when it
+    /// was last analyzed
+    files: HashMap<PathBuf, u64>,
+    buffers: HashMap<PathBuf, u64>,
+    stats: MemoStats,
+}
+
+impl Inner {
+    fn touch(&mut self, key: &Key) -> Option<Vec<Finding>> {
+        self.tick += 1;
+        let tick = self.tick;
+        let entry = self.entries.get_mut(key)?;
+        self.lru.remove(&entry.tick);
+        entry.tick = tick;
+        self.lru.insert(tick, key.clone());
+        Some(entry.findings.clone())
+    }
+
+    fn insert(&mut self, key: Key, findings: Vec<Finding>) {
+        let bytes = weight(&key, &findings);
+        if bytes > self.capacity {
+            return;
+        }
+        self.tick += 1;
+        if let Some(old) = self.entries.remove(&key) {
+            self.lru.remove(&old.tick);
+            self.stats.bytes -= old.bytes;
+        }
+        self.lru.insert(self.tick, key.clone());
+        self.entries.insert(
+            key,
+            Entry {
+                findings,
+                bytes,
+                tick: self.tick,
+            },
+        );
+        self.stats.bytes += bytes;
+        while self.stats.bytes > self.capacity {
+            let Some((_, oldest)) = self.lru.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&oldest) {
+                self.stats.bytes -= entry.bytes;
+                self.stats.evictions += 1;
+            }
+        }
+        self.stats.entries = self.entries.len();
+    }
+
+    /// Drop the entries `stale` selects, counting them as invalidated
+    fn invalidate(&mut self, stale: impl Fn(&Key) -> bool) {
+        let before = self.entries.len();
+        let lru = &mut self.lru;
+        let bytes = &mut self.stats.bytes;
+        self.entries.retain(|key, entry| {
+            if stale(key) {
+                lru.remove(&entry.tick);
+                *bytes -= entry.bytes;
+                false
+            } else {
+                true
+            }
+        });
+        self.stats.invalidations += before - self.entries.len();
+        self.stats.entries = self.entries.len();
+    }
+
+    /// Start a run under configuration `config`: drop everything of another
+    /// configuration
+    fn configure(&mut self, config: u64) {
+        if self.config.is_some_and(|last| last != config) {
+            self.invalidate(|key| key.config != config);
+        }
+        self.config = Some(config);
+    }
+}
+
+/// Estimated memory an entry takes
+fn weight(key: &Key, findings: &[Finding]) -> usize {
+    let path = match &key.scope {
+        Scope::File(path, _) | Scope::Buffer(path, _) => path.as_os_str().len(),
+        Scope::Files(_) => 0,
+    };
+    let findings: usize = findings
+        .iter()
+        .map(|f| {
+            std::mem::size_of::<Finding>()
+                + f.id.len()
+                + f.message.len()
+                + f.file.as_os_str().len()
+                + f.suggestion.as_ref().map_or(0, String::len)
+                + f.rule.as_ref().map_or(0, String::len)
+                + f.details
+                    .as_ref()
+                    .map_or(0, |d| d.lines().iter().map(String::len).sum::<usize>())
+        })
+        .sum();
+    ENTRY_OVERHEAD + key.analyzer.len() + path + findings
+}
+
+/// Hash of file or buffer contents
+pub(crate) fn content_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hash of what every analyzer is built from: the revet version and the
+/// configuration
+fn config_hash(config: &RevetConfig) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    crate::VERSION.hash(&mut hasher);
+    // Through `Value`, whose maps are sorted, so equal configs hash equal
+    serde_json::to_value(config)
+        .map(|value| value.to_string())
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Analyzer findings held in memory, shared by every clone of the handle
+#[derive(Clone)]
+pub struct AnalysisMemo {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl AnalysisMemo {
+    /// A memo holding at most `capacity` bytes of findings
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                capacity,
+                ..Inner::default()
+            })),
+        }
+    }
+
+    /// Keep a memo for the rest of the process, sized by `[performance]
+    /// memo_mb`, and return it. A memo already kept is resized and kept;
+    /// with `memo_mb = 0`, it is dropped and `None` returned.
+    pub fn start_session(config: &RevetConfig) -> Option<AnalysisMemo> {
+        let capacity = config.performance.memo_bytes();
+        let mut session = session();
+        if capacity == 0 {
+            *session = None;
+            return None;
+        }
+        match session.as_ref() {
+            Some(memo) => memo.resize(capacity),
+            None => *session = Some(Self::new(capacity)),
+        }
+        session.clone()
+    }
+
+    /// The memo of the process's session, if one was started
+    pub fn session() -> Option<AnalysisMemo> {
+        session().clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Change the capacity, evicting down to it
+    pub fn resize(&self, capacity: usize) {
+        let mut inner = self.lock();
+        inner.capacity = capacity;
+        while inner.stats.bytes > capacity {
+            let Some((_, oldest)) = inner.lru.pop_first() else {
+                break;
+            };
+            if let Some(entry) = inner.entries.remove(&oldest) {
+                inner.stats.bytes -= entry.bytes;
+                inner.stats.evictions += 1;
+            }
+        }
+        inner.stats.entries = inner.entries.len();
+    }
+
+    /// Drop every entry, returning how many there were
+    pub fn clear(&self) -> usize {
+        let mut inner = self.lock();
+        let count = inner.entries.len();
+        inner.invalidate(|_| true);
+        inner.files.clear();
+        inner.buffers.clear();
+        count
+    }
+
+    /// Counts since the memo was created
+    pub fn stats(&self) -> MemoStats {
+        self.lock().stats
+    }
+
+    /// Start a run of analyzers under `config` on `files`: hash their
+    /// contents and drop the entries of every file whose content changed
+    /// since the last run. Files that cannot be read are not memoized.
+    pub(crate) fn begin(&self, config: &RevetConfig, files: &[PathBuf]) -> MemoRun {
+        let config = config_hash(config);
+        let hashes: HashMap<PathBuf, u64> = files
+            .par_iter()
+            .filter_map(|file| {
+                let bytes = std::fs::read(file).ok()?;
+                Some((file.clone(), content_hash(&bytes)))
+            })
+            .collect();
+
+        let mut inner = self.lock();
+        inner.configure(config);
+        let changed: HashSet<&PathBuf> = hashes
+            .iter()
+            .filter(|(file, hash)| inner.files.get(*file).is_some_and(|last| last != *hash))
+            .map(|(file, _)| file)
+            .collect();
+        if !changed.is_empty() {
+            // A file set holding a changed file has a new fingerprint now,
+            // so its entries would never be hit again
+            inner.invalidate(|key| match &key.scope {
+                Scope::File(path, _) => changed.contains(path),
+                Scope::Buffer(..) => false,
+                Scope::Files(_) => true,
+            });
+        }
+        for (file, hash) in &hashes {
+            inner.files.insert(file.clone(), *hash);
+        }
+        drop(inner);
+
+        MemoRun {
+            memo: self.clone(),
+            config,
+            hashes,
+        }
+    }
+
+    /// Findings of `analyzer` on an unsaved buffer of `file` with `content`,
+    /// computed by `analyze` unless held
+    pub(crate) fn buffer(
+        &self,
+        config: &RevetConfig,
+        analyzer: &str,
+        file: &Path,
+        content: &str,
+        analyze: impl FnOnce() -> Vec<Finding>,
+    ) -> Vec<Finding> {
+        let config = config_hash(config);
+        let hash = content_hash(content.as_bytes());
+        let key = Key {
+            analyzer: analyzer.to_string(),
+            config,
+            scope: Scope::Buffer(file.to_path_buf(), hash),
+        };
+        {
+            let mut inner = self.lock();
+            inner.configure(config);
+            if inner.buffers.get(file).is_some_and(|last| *last != hash) {
+                inner.invalidate(|key| {
+                    matches!(&key.scope, Scope::Buffer(path, h) if path == file && *h != hash)
+                });
+            }
+            inner.buffers.insert(file.to_path_buf(), hash);
+            if let Some(findings) = inner.touch(&key) {
+                inner.stats.hits += 1;
+                return findings;
+            }
+            inner.stats.misses += 1;
+        }
+        let findings = analyze();
+        self.lock().insert(key, findings.clone());
+        findings
+    }
+}
+
+/// One run of analyzers through an [`AnalysisMemo`], with the content
+/// hashes of its files
+pub(crate) struct MemoRun {
+    memo: AnalysisMemo,
+    config: u64,
+    hashes: HashMap<PathBuf, u64>,
+}
+
+impl MemoRun {
+    /// Findings of `analyzer` on `file`, computed by `analyze` unless held
+    pub(crate) fn file(
+        &self,
+        analyzer: &str,
+        file: &Path,
+        analyze: impl FnOnce() -> Vec<Finding>,
+    ) -> Vec<Finding> {
+        match self.hashes.get(file) {
+            Some(hash) => self.lookup(analyzer, Scope::File(file.to_path_buf(), *hash), analyze),
+            None => analyze(),
+        }
+    }
+
+    /// Findings of `analyzer` on all the run's files together, computed by
+    /// `analyze` unless held
+    pub(crate) fn files(
+        &self,
+        analyzer: &str,
+        analyze: impl FnOnce() -> Vec<Finding>,
+    ) -> Vec<Finding> {
+        let mut files: Vec<(&PathBuf, &u64)> = self.hashes.iter().collect();
+        files.sort();
+        let mut hasher = DefaultHasher::new();
+        files.hash(&mut hasher);
+        self.lookup(analyzer, Scope::Files(hasher.finish()), analyze)
+    }
+
+    fn lookup(
+        &self,
+        analyzer: &str,
+        scope: Scope,
+        analyze: impl FnOnce() -> Vec<Finding>,
+    ) -> Vec<Finding> {
+        let key = Key {
+            analyzer: analyzer.to_string(),
+            config: self.config,
+            scope,
+        };
+        {
+            let mut inner = self.memo.lock();
+            if let Some(findings) = inner.touch(&key) {
+                inner.stats.hits += 1;
+                return findings;
+            }
+            inner.stats.misses += 1;
+        }
+        let findings = analyze();
+        self.memo.lock().insert(key, findings.clone());
+        findings
+    }
+}
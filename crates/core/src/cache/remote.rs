@@ -0,0 +1,203 @@
+//! Remote cache shared between machines (`[cache.remote]`)
+//!
+//! A [`RemoteCache`] sits behind the local caches: a local miss asks the
+//! remote and keeps what it gets (read-through), and every local save is
+//! uploaded too (write-back). The transport is a [`RemoteStore`] — two calls,
+//! GET and PUT of a byte blob by key — so the CLI supplies HTTP and S3
+//! backends and tests supply in-memory ones.
+//!
+//! Keys are content-addressed: they are derived from the fingerprint of
+//! what an artifact was built from, never from when, by whom or where.
+//! Writing a key therefore always writes the same bytes, so jobs racing on a
+//! key cannot corrupt it and nothing ever reads, modifies and writes back.
+//! Every key is scoped to the revet version only. Uploaded graph fragments
+//! hold no paths and are applied to the file being parsed; uploaded findings
+//! hold paths relative to the repository root, which the caches re-root
+//! under [`RemoteCache::repo_root`] on download. Runners checking out to
+//! different directories share entries.
+//!
+//! The remote is best-effort. The first error prints one notice to stderr
+//! and turns it off for the rest of the run; everything keeps working from
+//! the local cache, and [`RemoteStats::errors`] records what happened.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Kind of artifact in a key: per-file graph fragments
+pub const GRAPH: &str = "graph";
+/// Kind of artifact in a key: domain analyzer findings
+pub const ANALYSIS: &str = "analysis";
+
+/// Byte storage addressed by key
+pub trait RemoteStore: Send + Sync {
+    /// The blob stored under `key`, or `None` when there is none
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Store `bytes` under `key`. Writing the same key twice is harmless:
+    /// keys are content-addressed, so both writes carry the same bytes.
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<()>;
+}
+
+impl<S: RemoteStore + ?Sized> RemoteStore for Arc<S> {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<()> {
+        (**self).put(key, bytes)
+    }
+}
+
+/// What the remote cache did during a run, for `--timings`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RemoteStats {
+    /// Local misses the remote had
+    pub hits: usize,
+    /// Local misses the remote did not have either
+    pub misses: usize,
+    /// Artifacts uploaded
+    pub uploads: usize,
+    /// Failed requests; after the first, the remote is no longer asked
+    pub errors: usize,
+}
+
+/// A [`RemoteStore`] used fail-open, with counters
+pub struct RemoteCache {
+    store: Box<dyn RemoteStore>,
+    /// Where the store is, for the notice
+    location: String,
+    /// The revet version, prefixed to every key
+    scope: String,
+    /// Root of the checkout, which downloaded paths are re-rooted under
+    repo_root: PathBuf,
+    disabled: AtomicBool,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+    uploads: AtomicUsize,
+    errors: AtomicUsize,
+}
+
+impl RemoteCache {
+    /// Use `store` (found at `location`) for the checkout at `repo_root`
+    pub fn new(store: Box<dyn RemoteStore>, location: &str, repo_root: &Path) -> Self {
+        Self {
+            store,
+            location: location.to_string(),
+            scope: crate::VERSION.to_string(),
+            repo_root: repo_root.to_path_buf(),
+            disabled: AtomicBool::new(false),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+            uploads: AtomicUsize::new(0),
+            errors: AtomicUsize::new(0),
+        }
+    }
+
+    /// A remote that is off from the start because it could not be set up;
+    /// prints the notice and counts one error
+    pub fn unavailable(location: &str, error: &anyhow::Error) -> Self {
+        struct Nowhere;
+        impl RemoteStore for Nowhere {
+            fn get(&self, _key: &str) -> Result<Option<Vec<u8>>> {
+                Ok(None)
+            }
+            fn put(&self, _key: &str, _bytes: &[u8]) -> Result<()> {
+                Ok(())
+            }
+        }
+        let cache = Self::new(Box::new(Nowhere), location, Path::new(""));
+        cache.fail(error);
+        cache
+    }
+
+    /// Root of the checkout this cache serves
+    pub fn repo_root(&self) -> &Path {
+        &self.repo_root
+    }
+
+    /// `path` relative to the repository root, as uploaded
+    pub fn relative(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.repo_root)
+            .unwrap_or(path)
+            .to_path_buf()
+    }
+
+    /// A downloaded `path` under this checkout's repository root
+    pub fn absolute(&self, path: &Path) -> PathBuf {
+        if path.as_os_str().is_empty() {
+            PathBuf::new()
+        } else {
+            self.repo_root.join(path)
+        }
+    }
+
+    /// Full key of artifact `id` of `kind` ([`GRAPH`] or [`ANALYSIS`])
+    pub fn key(&self, kind: &str, id: &str) -> String {
+        format!("{}/{}/{}", self.scope, kind, id)
+    }
+
+    /// The blob for `id` of `kind`, or `None` on a miss, an error or once
+    /// the remote is off
+    pub fn get(&self, kind: &str, id: &str) -> Option<Vec<u8>> {
+        if self.disabled.load(Ordering::Relaxed) {
+            return None;
+        }
+        match self.store.get(&self.key(kind, id)) {
+            Ok(Some(bytes)) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(bytes)
+            }
+            Ok(None) => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            Err(e) => {
+                self.fail(&e);
+                None
+            }
+        }
+    }
+
+    /// Upload `bytes` as `id` of `kind`, unless the remote is off
+    pub fn put(&self, kind: &str, id: &str, bytes: &[u8]) {
+        if self.disabled.load(Ordering::Relaxed) {
+            return;
+        }
+        match self.store.put(&self.key(kind, id), bytes) {
+            Ok(()) => {
+                self.uploads.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => self.fail(&e),
+        }
+    }
+
+    /// Whether an error has turned the remote off
+    pub fn is_disabled(&self) -> bool {
+        self.disabled.load(Ordering::Relaxed)
+    }
+
+    /// Counters so far
+    pub fn stats(&self) -> RemoteStats {
+        RemoteStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            uploads: self.uploads.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Count the error; the first one turns the remote off with a notice
+    fn fail(&self, error: &anyhow::Error) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+        if !self.disabled.swap(true, Ordering::Relaxed) {
+            eprintln!(
+                "  warn: remote cache {} unavailable, using the local cache only: {:#}",
+                self.location, error
+            );
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Threshold calibration for `revet calibrate`.
+//!
+//! Threshold-based analyzers can report the raw metric behind a rule
+//! instead of comparing it ([`Analyzer::measure`](crate::Analyzer::measure),
+//! [`GraphAnalyzer::measure`](crate::GraphAnalyzer::measure)). A [`Metric`]
+//! holds the measured values and how many findings each candidate threshold
+//! would produce; [`Metric::suggest`] picks the lowest candidate that keeps
+//! the rule within a [`Budget`]. Nothing here changes the configuration —
+//! [`toml_section`] only renders the suggestions as comments to paste.
+
+use crate::config::CalibrationConfig;
+
+/// The raw distribution behind one threshold setting
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    /// Setting the threshold is read from, e.g. `modules.complexity_threshold`
+    pub setting: &'static str,
+    /// What one value measures, e.g. "cyclomatic complexity per function"
+    pub label: &'static str,
+    /// Decimals the threshold is set with (0 for counts)
+    pub decimals: usize,
+    /// Threshold in effect
+    pub current: f64,
+    /// One value per measured item, ascending
+    pub values: Vec<f64>,
+    /// `(threshold, findings)` per candidate threshold, ascending by threshold
+    pub candidates: Vec<(f64, usize)>,
+}
+
+impl Metric {
+    /// A metric whose rule flags every item with a value of at least the
+    /// threshold. Candidates are each distinct value (rounded up to
+    /// `decimals`) of at least `min`, the current threshold, and the first
+    /// threshold that flags nothing.
+    pub fn at_least(
+        setting: &'static str,
+        label: &'static str,
+        decimals: usize,
+        current: f64,
+        min: f64,
+        mut values: Vec<f64>,
+    ) -> Self {
+        values.sort_by(f64::total_cmp);
+        let step = 10f64.powi(-(decimals as i32));
+        let round_up = |v: f64| {
+            let scale = 10f64.powi(decimals as i32);
+            (v * scale - 1e-9).ceil() / scale
+        };
+        let mut thresholds: Vec<f64> = values
+            .iter()
+            .map(|&v| round_up(v).max(min))
+            .chain(std::iter::once(current))
+            .collect();
+        if let Some(&max) = values.last() {
+            thresholds.push((round_up(max) + step).max(min));
+        }
+        thresholds.sort_by(f64::total_cmp);
+        thresholds.dedup_by(|a, b| (*a - *b).abs() < step / 2.0);
+        let candidates = thresholds
+            .into_iter()
+            .map(|t| (t, values.iter().filter(|&&v| v >= t - 1e-9).count()))
+            .collect();
+        Self {
+            setting,
+            label,
+            decimals,
+            current,
+            values,
+            candidates,
+        }
+    }
+
+    /// The lowest candidate threshold, and its findings, within `budget`
+    pub fn suggest(&self, budget: &Budget) -> Option<(f64, usize)> {
+        let allowed = budget.findings(self.values.len());
+        self.candidates
+            .iter()
+            .copied()
+            .find(|&(_, findings)| findings <= allowed)
+    }
+
+    /// Findings the current threshold produces, when it was a candidate
+    pub fn current_findings(&self) -> Option<usize> {
+        match self.candidates.last() {
+            Some(&(last, 0)) if self.current > last => Some(0),
+            _ => self
+                .candidates
+                .iter()
+                .find(|(t, _)| (t - self.current).abs() < 1e-9)
+                .map(|&(_, findings)| findings),
+        }
+    }
+
+    /// The value at percentile `p` (nearest rank)
+    pub fn percentile(&self, p: u32) -> Option<f64> {
+        if self.values.is_empty() {
+            return None;
+        }
+        let rank = (self.values.len() * p as usize).div_ceil(100).max(1);
+        Some(self.values[rank - 1])
+    }
+
+    /// `value` with the metric's decimals
+    pub fn format(&self, value: f64) -> String {
+        format!("{:.*}", self.decimals, value)
+    }
+
+    /// `count 40, min 1, p50 4, p90 12, p95 15, p99 19, max 19`
+    pub fn summary(&self) -> String {
+        let (Some(min), Some(max)) = (self.values.first(), self.values.last()) else {
+            return "count 0".to_string();
+        };
+        let mut out = format!("count {}, min {}", self.values.len(), self.format(*min));
+        for p in [50, 90, 95, 99] {
+            if let Some(v) = self.percentile(p) {
+                out.push_str(&format!(", p{} {}", p, self.format(v)));
+            }
+        }
+        out.push_str(&format!(", max {}", self.format(*max)));
+        out
+    }
+}
+
+/// How many findings a suggested threshold may leave per rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    /// Flag at most the items above this percentile of the distribution
+    Percentile(u8),
+    /// Flag at most this many items
+    MaxFindings(usize),
+}
+
+impl Budget {
+    /// `[calibration] max_findings` when set, else `percentile`
+    pub fn from_config(config: &CalibrationConfig) -> Self {
+        match config.max_findings {
+            Some(n) => Budget::MaxFindings(n),
+            None => Budget::Percentile(config.percentile),
+        }
+    }
+
+    /// Findings allowed for a metric of `items` values
+    pub fn findings(&self, items: usize) -> usize {
+        match *self {
+            Budget::Percentile(p) => items * 100usize.saturating_sub(p as usize) / 100,
+            Budget::MaxFindings(n) => n,
+        }
+    }
+
+    /// `at most 5% of items flagged per rule` / `at most 20 findings per rule`
+    pub fn describe(&self) -> String {
+        match *self {
+            Budget::Percentile(p) => format!(
+                "at most {}% of measured items flagged per rule (percentile = {})",
+                100u8.saturating_sub(p),
+                p
+            ),
+            Budget::MaxFindings(n) => format!("at most {} finding(s) per rule", n),
+        }
+    }
+}
+
+/// A commented `[calibration]` section recording `budget`, with each
+/// metric's suggestion as a commented setting to copy into its own table
+pub fn toml_section(metrics: &[Metric], budget: &Budget) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "# Suggested by `revet calibrate`: {}.\n",
+        budget.describe()
+    ));
+    out.push_str("# Nothing is applied: copy a setting into its table to use it.\n");
+    out.push_str("[calibration]\n");
+    match budget {
+        Budget::Percentile(p) => out.push_str(&format!("percentile = {}\n", p)),
+        Budget::MaxFindings(n) => out.push_str(&format!("max_findings = {}\n", n)),
+    }
+    for metric in metrics {
+        let (table, key) = metric
+            .setting
+            .rsplit_once('.')
+            .unwrap_or(("", metric.setting));
+        out.push('\n');
+        out.push_str(&format!("# {}: {}\n", metric.label, metric.summary()));
+        let current = match metric.current_findings() {
+            Some(n) => format!("{} finding(s)", n),
+            None => "not measured".to_string(),
+        };
+        match metric.suggest(budget) {
+            Some((threshold, findings)) => {
+                out.push_str(&format!(
+                    "# {} finding(s) at {} (currently {}: {})\n",
+                    findings,
+                    metric.format(threshold),
+                    metric.format(metric.current),
+                    current
+                ));
+                out.push_str(&format!("# [{}]\n", table));
+                out.push_str(&format!("# {} = {}\n", key, metric.format(threshold)));
+            }
+            None => out.push_str(&format!(
+                "# no measured threshold is within the budget (currently {}: {})\n",
+                metric.format(metric.current),
+                current
+            )),
+        }
+    }
+    out
+}
@@ -0,0 +1,659 @@
+//! Debug cases — one finding, bundled so its analyzer can be re-run alone
+//!
+//! `revet debug capture` packs what one analyzer needs to report a finding
+//! again, away from the repository it came from: the file (optionally
+//! redacted), the slice of the effective config that analyzer reads, the
+//! finding itself and, for graph analyzers, the file's neighbourhood in the
+//! cached graph. `revet debug replay` unpacks it into a scratch directory and
+//! runs only that analyzer, optionally with a [`trace`](crate::trace).
+//!
+//! A case is a gzipped tar archive:
+//!
+//! ```text
+//! case.json      manifest: format version, revet version, analyzer, finding
+//! config.toml    config slice
+//! files/<path>   the file, at its repository-relative path
+//! graph.json     file neighbourhood with relative paths (graph analyzers)
+//! ```
+
+use crate::analyzer::registry;
+use crate::analyzer::secret_exposure::SecretExposureAnalyzer;
+use crate::analyzer::{Analyzer, AnalyzerDispatcher};
+use crate::cache::{CacheFormat, GraphCache};
+use crate::config::{ModulesConfig, RevetConfig};
+use crate::finding::{Finding, Severity};
+use crate::graph::CodeGraph;
+use crate::redact::redact_spans;
+use crate::storage::StoragePaths;
+use crate::trace::{self, TraceEvent};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Version of the archive layout and manifest; bumped on breaking changes
+pub const FORMAT_VERSION: u32 = 1;
+
+/// File extension of a case archive
+pub const EXTENSION: &str = "revetcase";
+
+const MANIFEST: &str = "case.json";
+const CONFIG: &str = "config.toml";
+const FILES: &str = "files/";
+const GRAPH: &str = "graph.json";
+
+/// Lines kept on each side of the finding when redacting
+pub const DEFAULT_CONTEXT: usize = 3;
+
+/// Config read by an analyzer besides `[modules]` and
+/// `[analyzers.<module>]`, as dotted paths
+const SECTIONS: &[(&str, &[&str])] = &[
+    (
+        "SEC",
+        &["analyzers.secrets", "output.redact_secrets", "files"],
+    ),
+    ("MAGIC", &["magic_numbers"]),
+    ("RES", &["resource_leaks"]),
+    ("LOG", &["sensitive_logging"]),
+    ("K8S", &["kubernetes"]),
+    ("INFRA", &["modules.kubernetes"]),
+    ("DUP", &["modules.duplication_min_lines"]),
+    ("I18N", &["analyzers.i18n"]),
+    ("TEST", &["files"]),
+    ("GEN", &["analyzers.generated", "files"]),
+    ("CMPLX", &["modules.complexity_threshold", "parser"]),
+    ("DEAD", &["parser"]),
+    ("CYCLE", &["parser"]),
+    ("IMP", &["parser"]),
+    ("COV", &["parser", "files"]),
+    ("COUPLE", &["parser", "files", "vendored"]),
+    ("DOC", &["parser", "files", "vendored", "project"]),
+    ("RACE", &["parser", "files", "vendored"]),
+];
+
+/// The manifest of a case (`case.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseManifest {
+    pub format_version: u32,
+    /// Version of the revet that captured the case
+    pub revet_version: String,
+    /// Unix time of the capture
+    pub created: u64,
+    pub analyzer: CaseAnalyzer,
+    /// Repository-relative path of the bundled file, `/`-separated
+    pub file: String,
+    /// The finding being reproduced; its file is repository-relative
+    pub finding: Finding,
+    /// How the bundled file was redacted; `None` when it is bundled whole
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redaction: Option<Redaction>,
+}
+
+/// The analyzer a case re-runs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaseAnalyzer {
+    /// Finding prefix (`SEC`)
+    pub prefix: String,
+    pub name: String,
+    /// Sub-rule module, when the analyzer has sub-rules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    /// Runs on the code graph rather than on file content
+    pub graph: bool,
+    /// Analyzers are versioned with revet itself
+    pub version: String,
+}
+
+/// Which lines of the bundled file survived redaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Redaction {
+    /// Lines kept on each side of the finding
+    pub context: usize,
+    /// Kept 1-based line ranges, inclusive; every other line is blanked
+    pub kept: Vec<(usize, usize)>,
+    /// Kept lines whose secret values were masked
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub masked: Vec<usize>,
+}
+
+/// The finding to capture, as a run log records it
+#[derive(Debug, Clone)]
+pub struct CaseTarget {
+    /// Finding ID in the run (`SEC-004`)
+    pub id: String,
+    /// Repository-relative file
+    pub file: PathBuf,
+    pub line: usize,
+    pub severity: Severity,
+    /// Qualified sub-rule (`security:secret`)
+    pub rule: Option<String>,
+    /// Message with secrets masked
+    pub message: String,
+}
+
+/// How to capture a case
+#[derive(Debug, Clone, Default)]
+pub struct CaptureOptions {
+    /// Blank every line further than this from the finding (and outside the
+    /// graph nodes spanning it) and mask secrets on the rest. Required when
+    /// the file contains secrets.
+    pub redact: Option<usize>,
+}
+
+/// A captured case, in memory
+#[derive(Debug, Clone)]
+pub struct Case {
+    pub manifest: CaseManifest,
+    /// The config slice, as TOML
+    pub config: String,
+    /// Content of the bundled file, after redaction
+    pub source: String,
+    /// Neighbourhood of the file in the graph, paths relative to the
+    /// repository root (graph analyzers only)
+    pub graph: Option<CodeGraph>,
+}
+
+/// Outcome of replaying a case
+#[derive(Debug, Clone)]
+pub struct Replay {
+    /// The analyzer reported the captured finding again
+    pub reproduced: bool,
+    /// Everything the analyzer reported on the file, paths relative
+    pub findings: Vec<Finding>,
+    /// Decisions recorded while it ran (empty without `--trace`)
+    pub trace: Vec<TraceEvent>,
+}
+
+/// Bundle `target` from the repository at `repo_root`.
+///
+/// Fails when its analyzer cannot run on its own, when the file is gone,
+/// when it contains secrets and `options` does not redact, or when a graph
+/// analyzer's file is not in the cached graph. A finding the analyzer no
+/// longer reports on the file alone is still bundled, as the run log has it.
+pub fn capture(
+    target: &CaseTarget,
+    repo_root: &Path,
+    config: &RevetConfig,
+    paths: &StoragePaths,
+    options: &CaptureOptions,
+) -> Result<Case> {
+    let prefix = registry::prefix_of(&target.id);
+    let dispatcher = AnalyzerDispatcher::new_with_config(config);
+    let analyzer = describe(&dispatcher, prefix).ok_or_else(|| {
+        anyhow!(
+            "{} findings do not come from an analyzer that can run on its own",
+            prefix
+        )
+    })?;
+
+    let file = repo_root.join(&target.file);
+    let content = std::fs::read_to_string(&file)
+        .with_context(|| format!("failed to read {}", target.file.display()))?;
+    let relative = portable(&target.file);
+
+    let secrets = SecretExposureAnalyzer::with_redaction(true)
+        .with_patterns(&config.analyzers.secrets.patterns)
+        .with_min_entropy(config.analyzers.secrets.min_entropy)
+        .with_files(&config.files)
+        .analyze_source(&file, &content, repo_root);
+    if !secrets.is_empty() && options.redact.is_none() {
+        let lines: Vec<String> = secrets.iter().map(|f| f.line.to_string()).collect();
+        bail!(
+            "{} contains secrets (line {}); capture it with --redact",
+            relative,
+            lines.join(", ")
+        );
+    }
+
+    let full_graph = if analyzer.graph {
+        let cache = GraphCache::at(paths, CacheFormat::from_config(&config.cache));
+        let (graph, _) = cache.load()?.ok_or_else(|| {
+            anyhow!(
+                "no cached graph — run `revet review` before capturing {} findings",
+                prefix
+            )
+        })?;
+        Some(graph)
+    } else {
+        None
+    };
+    let graph = match &full_graph {
+        Some(full) => {
+            let neighbourhood = full.neighbourhood(&file);
+            if neighbourhood.nodes().next().is_none() {
+                bail!(
+                    "the cached graph has no nodes for {} — run `revet review` to refresh it",
+                    relative
+                );
+            }
+            Some(neighbourhood.relocated(PathBuf::new(), |p| {
+                p.strip_prefix(repo_root).unwrap_or(p).to_path_buf()
+            }))
+        }
+        None => None,
+    };
+
+    let findings = dispatcher
+        .run_one(
+            prefix,
+            std::slice::from_ref(&file),
+            full_graph.as_ref(),
+            repo_root,
+            config,
+        )
+        .unwrap_or_default();
+    let mut finding = findings
+        .iter()
+        .filter(|f| f.line == target.line && f.rule == target.rule)
+        .max_by_key(|f| f.redacted_message() == target.message)
+        .cloned()
+        .unwrap_or_else(|| Finding {
+            severity: target.severity,
+            message: target.message.clone(),
+            line: target.line,
+            rule: target.rule.clone(),
+            ..Finding::default()
+        });
+    finding.id = target.id.clone();
+    finding.message = finding.redacted_message().to_string();
+    finding.redacted_message = None;
+    finding.file = target.file.clone();
+
+    let (source, redaction) = match options.redact {
+        Some(context) => {
+            let spans = spanning_nodes(graph.as_ref(), &target.file, target.line);
+            let (source, redaction) = redact(&content, target.line, context, &spans, &secrets);
+            (source, Some(redaction))
+        }
+        None => (content, None),
+    };
+
+    Ok(Case {
+        manifest: CaseManifest {
+            format_version: FORMAT_VERSION,
+            revet_version: crate::VERSION.to_string(),
+            created: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            analyzer,
+            file: relative,
+            finding,
+            redaction,
+        },
+        config: config_slice(config, &dispatcher, prefix)?,
+        source,
+        graph,
+    })
+}
+
+/// Name, module and kind of the analyzer reporting under `prefix`
+fn describe(dispatcher: &AnalyzerDispatcher, prefix: &str) -> Option<CaseAnalyzer> {
+    let (name, module, graph) = match dispatcher.analyzer(prefix) {
+        Some(a) => (a.name().to_string(), a.module(), false),
+        None => {
+            let a = dispatcher.graph_analyzer(prefix)?;
+            (a.name().to_string(), a.module(), true)
+        }
+    };
+    Some(CaseAnalyzer {
+        prefix: prefix.to_string(),
+        name,
+        module: module.map(str::to_string),
+        graph,
+        version: crate::VERSION.to_string(),
+    })
+}
+
+/// Whether the analyzer reporting under `prefix` is enabled by `config`
+fn is_enabled(dispatcher: &AnalyzerDispatcher, prefix: &str, config: &RevetConfig) -> bool {
+    match dispatcher.analyzer(prefix) {
+        Some(a) => a.is_enabled(config),
+        None => dispatcher
+            .graph_analyzer(prefix)
+            .is_some_and(|a| a.is_enabled(config)),
+    }
+}
+
+/// The parts of `config` the analyzer reporting under `prefix` reads, as
+/// TOML: the module switches it depends on (every other one off), its
+/// `[analyzers.<module>]` table, the sections in [`SECTIONS`] and, for
+/// custom rules, the `[[rules]]` with its prefix
+fn config_slice(
+    config: &RevetConfig,
+    dispatcher: &AnalyzerDispatcher,
+    prefix: &str,
+) -> Result<String> {
+    let full = match toml::Value::try_from(config).context("failed to serialize the config")? {
+        toml::Value::Table(table) => table,
+        _ => toml::Table::new(),
+    };
+    let mut slice = toml::Table::new();
+
+    let mut modules = toml::Table::new();
+    for &name in ModulesConfig::NAMES {
+        let mut without = config.clone();
+        without.modules.set(name, false);
+        let needed =
+            is_enabled(dispatcher, prefix, config) && !is_enabled(dispatcher, prefix, &without);
+        modules.insert(name.to_string(), toml::Value::Boolean(needed));
+    }
+    slice.insert("modules".to_string(), toml::Value::Table(modules));
+
+    let module = dispatcher
+        .analyzer(prefix)
+        .map(|a| a.module())
+        .or_else(|| dispatcher.graph_analyzer(prefix).map(|a| a.module()))
+        .flatten();
+    let mut keys: Vec<String> = module
+        .map(|m| format!("analyzers.{}", m))
+        .into_iter()
+        .collect();
+    if let Some((_, sections)) = SECTIONS.iter().find(|(p, _)| *p == prefix) {
+        keys.extend(sections.iter().map(|s| s.to_string()));
+    }
+    for key in &keys {
+        copy_path(&full, &mut slice, key);
+    }
+
+    if !registry::is_builtin(prefix) {
+        let rules: Vec<toml::Value> = config
+            .rules
+            .iter()
+            .filter(|r| r.prefix.as_deref().unwrap_or(registry::CUSTOM) == prefix)
+            .map(toml::Value::try_from)
+            .collect::<Result<_, _>>()
+            .context("failed to serialize [[rules]]")?;
+        slice.insert("rules".to_string(), toml::Value::Array(rules));
+    }
+
+    toml::to_string_pretty(&slice).context("failed to serialize the config slice")
+}
+
+/// Copy the value at dotted `path` of `from` into `to`, creating tables
+fn copy_path(from: &toml::Table, to: &mut toml::Table, path: &str) {
+    let mut source = from;
+    let mut target = to;
+    let mut parts = path.split('.').peekable();
+    while let Some(part) = parts.next() {
+        let Some(value) = source.get(part) else {
+            return;
+        };
+        if parts.peek().is_none() {
+            target.insert(part.to_string(), value.clone());
+            return;
+        }
+        let Some(table) = value.as_table() else {
+            return;
+        };
+        source = table;
+        let entry = target
+            .entry(part.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        let Some(next) = entry.as_table_mut() else {
+            return;
+        };
+        target = next;
+    }
+}
+
+/// Line ranges of the graph nodes of `file` that span `line`, so redaction
+/// keeps the whole function a graph finding measures
+fn spanning_nodes(graph: Option<&CodeGraph>, file: &Path, line: usize) -> Vec<(usize, usize)> {
+    let Some(graph) = graph else {
+        return Vec::new();
+    };
+    graph
+        .nodes()
+        .map(|(_, node)| node)
+        .filter(|node| node.file_path() == file)
+        .map(|node| (node.line(), node.end_line().unwrap_or(node.line())))
+        .filter(|&(start, end)| start <= line && line <= end)
+        .collect()
+}
+
+/// Blank the lines of `content` outside `context` lines around `line` and
+/// outside `spans`, and mask the secrets `secrets` found on the rest. Line
+/// numbers are unchanged.
+fn redact(
+    content: &str,
+    line: usize,
+    context: usize,
+    spans: &[(usize, usize)],
+    secrets: &[Finding],
+) -> (String, Redaction) {
+    let count = content.lines().count();
+    let mut kept: Vec<(usize, usize)> = spans.to_vec();
+    kept.push((
+        line.saturating_sub(context).max(1),
+        (line + context).min(count),
+    ));
+    kept.sort_unstable();
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in kept {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    let mut masked = Vec::new();
+    let mut out = String::with_capacity(content.len());
+    for (i, text) in content.lines().enumerate() {
+        let number = i + 1;
+        if merged.iter().any(|&(s, e)| s <= number && number <= e) {
+            let spans: Vec<(usize, usize)> = secrets
+                .iter()
+                .filter(|f| f.line == number)
+                .flat_map(|f| f.secret_spans.iter().copied())
+                .collect();
+            if secrets.iter().any(|f| f.line == number) {
+                masked.push(number);
+            }
+            out.push_str(&redact_spans(text, &spans));
+        }
+        out.push('\n');
+    }
+    if !content.ends_with('\n') {
+        out.pop();
+    }
+    (
+        out,
+        Redaction {
+            context,
+            kept: merged,
+            masked,
+        },
+    )
+}
+
+/// `path` with `/` separators
+fn portable(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+impl Case {
+    /// Write the case archive to `path`
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("failed to create {}", path.display()))?;
+        let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        let mut archive = tar::Builder::new(encoder);
+        let mtime = self.manifest.created;
+
+        let manifest = serde_json::to_vec_pretty(&self.manifest)?;
+        append(&mut archive, MANIFEST, &manifest, mtime)?;
+        append(&mut archive, CONFIG, self.config.as_bytes(), mtime)?;
+        let name = format!("{}{}", FILES, self.manifest.file);
+        append(&mut archive, &name, self.source.as_bytes(), mtime)?;
+        if let Some(graph) = &self.graph {
+            append(&mut archive, GRAPH, &serde_json::to_vec(graph)?, mtime)?;
+        }
+        archive
+            .into_inner()?
+            .finish()
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Read a case archive written by [`write`](Self::write)
+    pub fn read(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        let mut entries: HashMap<String, Vec<u8>> = HashMap::new();
+        for entry in archive
+            .entries()
+            .with_context(|| format!("{} is not a case archive", path.display()))?
+        {
+            let mut entry = entry?;
+            let name = portable(&entry.path()?);
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+            entries.insert(name, data);
+        }
+
+        let manifest = entries
+            .get(MANIFEST)
+            .ok_or_else(|| anyhow!("{} has no {}", path.display(), MANIFEST))?;
+        let version: serde_json::Value = serde_json::from_slice(manifest)?;
+        let version = version["format_version"].as_u64().unwrap_or(0);
+        if version > u64::from(FORMAT_VERSION) {
+            bail!(
+                "case format {} is newer than this revet reads ({}) — upgrade revet",
+                version,
+                FORMAT_VERSION
+            );
+        }
+        let manifest: CaseManifest =
+            serde_json::from_slice(manifest).context("invalid case manifest")?;
+        let relative = Path::new(&manifest.file);
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            bail!("case file path {:?} leaves the case", manifest.file);
+        }
+
+        let text = |name: &str| -> Result<String> {
+            let data = entries
+                .get(name)
+                .ok_or_else(|| anyhow!("{} has no {}", path.display(), name))?;
+            String::from_utf8(data.clone()).with_context(|| format!("{} is not UTF-8", name))
+        };
+        let config = text(CONFIG)?;
+        let source = text(&format!("{}{}", FILES, manifest.file))?;
+        let graph = match entries.get(GRAPH) {
+            Some(data) => Some(serde_json::from_slice(data).context("invalid case graph")?),
+            None => None,
+        };
+        Ok(Self {
+            manifest,
+            config,
+            source,
+            graph,
+        })
+    }
+
+    /// Run the case's analyzer alone on the bundled file, in a scratch
+    /// directory, and report whether it finds the captured finding again
+    /// (same line and sub-rule). With `trace`, also collect its decisions.
+    pub fn replay(&self, trace: bool) -> Result<Replay> {
+        let config: RevetConfig =
+            toml::from_str(&self.config).context("invalid config in the case")?;
+        let scratch = Scratch::create()?;
+        let root = scratch.0.as_path();
+        let file = root.join(&self.manifest.file);
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&file, &self.source)?;
+        let graph = self
+            .graph
+            .as_ref()
+            .map(|g| g.relocated(root.to_path_buf(), |p| root.join(p)));
+
+        let prefix = self.manifest.analyzer.prefix.as_str();
+        let dispatcher = AnalyzerDispatcher::new_with_config(&config);
+        let run = || {
+            dispatcher.run_one(
+                prefix,
+                std::slice::from_ref(&file),
+                graph.as_ref(),
+                root,
+                &config,
+            )
+        };
+        let (findings, mut events) = if trace {
+            trace::collect(prefix, run)
+        } else {
+            (run(), Vec::new())
+        };
+        let mut findings = findings.ok_or_else(|| {
+            anyhow!(
+                "this revet has no {} analyzer ({}) that can run on its own",
+                prefix,
+                self.manifest.analyzer.name
+            )
+        })?;
+
+        let relative = |p: &Path| p.strip_prefix(root).unwrap_or(p).to_path_buf();
+        for finding in &mut findings {
+            finding.file = relative(&finding.file);
+        }
+        for event in &mut events {
+            event.file = relative(&event.file);
+        }
+        let wanted = &self.manifest.finding;
+        let reproduced = findings
+            .iter()
+            .any(|f| f.line == wanted.line && f.rule == wanted.rule);
+        Ok(Replay {
+            reproduced,
+            findings,
+            trace: events,
+        })
+    }
+}
+
+fn append<W: std::io::Write>(
+    archive: &mut tar::Builder<W>,
+    name: &str,
+    data: &[u8],
+    mtime: u64,
+) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(mtime);
+    header.set_cksum();
+    archive
+        .append_data(&mut header, name, data)
+        .with_context(|| format!("failed to add {} to the case", name))
+}
+
+/// Scratch directory for a replay, removed when dropped
+struct Scratch(PathBuf);
+
+impl Scratch {
+    fn create() -> Result<Self> {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "revet-case-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+        Ok(Self(dir.canonicalize().unwrap_or(dir)))
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,169 @@
+//! Change frequency from git history, for hotspot analysis
+//!
+//! One pass over the commits reachable from HEAD counts how many non-merge
+//! commits inside a time window touched each path. Renames are followed, so
+//! a file keeps the churn it had under its old name. The window ends at the
+//! HEAD commit's time, which makes the result a function of HEAD alone: it is
+//! cached in `churn.json` of the state directory keyed on the HEAD commit and window,
+//! and repeated runs on the same commit don't walk the history again.
+//!
+//! In a shallow clone the walk stops at the shallow boundary; the commits
+//! that are there are counted and [`Churn::shallow`] is set.
+
+use anyhow::{Context, Result};
+use git2::{DiffFindOptions, Repository, Sort};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::git_objects::is_partial_clone;
+use crate::storage::{StoragePaths, CHURN_FILE};
+
+const DAY_SECS: i64 = 24 * 3600;
+
+/// Length of the history window, parsed from `"90d"`, `"12w"`, `"6m"` or `"1y"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Window {
+    pub days: u32,
+}
+
+impl Window {
+    /// Parse `<n>d`, `<n>w`, `<n>m` (30 days) or `<n>y` (365 days); `n` > 0
+    pub fn parse(spec: &str) -> Option<Self> {
+        let spec = spec.trim();
+        let unit = spec.chars().last()?;
+        let n: u32 = spec[..spec.len() - unit.len_utf8()].parse().ok()?;
+        let per_unit = match unit {
+            'd' => 1,
+            'w' => 7,
+            'm' => 30,
+            'y' => 365,
+            _ => return None,
+        };
+        let days = n.checked_mul(per_unit)?;
+        (days > 0).then_some(Self { days })
+    }
+}
+
+/// Per-path change counts over one window of history
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Churn {
+    /// HEAD commit the counts were taken at
+    pub head: String,
+    /// Window length in days
+    pub window_days: u32,
+    /// Non-merge commits inside the window
+    pub commits: usize,
+    /// The repository is a shallow clone, so older history may be missing
+    pub shallow: bool,
+    /// The repository is a partial clone, so only renames that keep the
+    /// file's contents are followed
+    #[serde(default)]
+    pub partial: bool,
+    /// Commits touching each path, keyed by the path's name at HEAD
+    /// (relative to the repository root, `/`-separated)
+    pub changes: BTreeMap<String, usize>,
+}
+
+impl Churn {
+    /// Commits touching `path` (relative to the repository root)
+    pub fn changes(&self, path: &Path) -> usize {
+        let key = path.to_string_lossy().replace('\\', "/");
+        self.changes.get(&key).copied().unwrap_or(0)
+    }
+
+    /// Load the cached counts for the current HEAD and `window`, or walk the
+    /// history and cache the result. Caching is best-effort.
+    pub fn load_or_compute(repo_root: &Path, paths: &StoragePaths, window: Window) -> Result<Self> {
+        let repo = Repository::discover(repo_root).context("Not a git repository")?;
+        let head = repo.head()?.peel_to_commit()?.id().to_string();
+        let cache_path = paths.write_path(CHURN_FILE);
+
+        if let Some(cached) = std::fs::read(paths.read_path(CHURN_FILE))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Churn>(&bytes).ok())
+        {
+            if cached.head == head && cached.window_days == window.days {
+                return Ok(cached);
+            }
+        }
+
+        let churn = Self::compute(&repo, window)?;
+        if let Some(parent) = cache_path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_vec(&churn) {
+            let _ = std::fs::write(&cache_path, json);
+        }
+        Ok(churn)
+    }
+
+    /// Walk the history of `repo` once, newest commit first
+    pub fn compute(repo: &Repository, window: Window) -> Result<Self> {
+        let head = repo.head()?.peel_to_commit()?;
+        let cutoff = head.time().seconds() - i64::from(window.days) * DAY_SECS;
+        let shallow = repo.is_shallow();
+        let partial = is_partial_clone(repo);
+
+        let mut walk = repo.revwalk()?;
+        walk.push(head.id())?;
+        walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+        let mut churn = Churn {
+            head: head.id().to_string(),
+            window_days: window.days,
+            shallow,
+            partial,
+            ..Default::default()
+        };
+        // Old name → name at HEAD, for renames seen so far. Children are
+        // walked before parents, so a rename is known before older commits
+        // touch the old name.
+        let mut renamed: HashMap<String, String> = HashMap::new();
+        let mut find = DiffFindOptions::new();
+        find.renames(true);
+        // Similarity needs the contents of both sides, which a partial
+        // clone mostly lacks
+        find.exact_match_only(partial);
+
+        for oid in walk {
+            let commit = match oid.and_then(|oid| repo.find_commit(oid)) {
+                Ok(commit) => commit,
+                // Missing objects past a shallow boundary
+                Err(_) if shallow => break,
+                Err(e) => return Err(e.into()),
+            };
+            if commit.parent_count() > 1 || commit.time().seconds() < cutoff {
+                continue;
+            }
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree()?),
+                // Shallow boundary (grafted as a root): what this commit
+                // changed is unknown
+                Err(_) if shallow => continue,
+                Err(_) if commit.parent_count() == 0 => None,
+                Err(e) => return Err(e.into()),
+            };
+            let tree = commit.tree()?;
+            let mut diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+            diff.find_similar(Some(&mut find))?;
+
+            churn.commits += 1;
+            for delta in diff.deltas() {
+                let Some(path) = delta.new_file().path().or(delta.old_file().path()) else {
+                    continue;
+                };
+                let path = path.to_string_lossy().replace('\\', "/");
+                let current = renamed.get(&path).cloned().unwrap_or(path);
+                if delta.status() == git2::Delta::Renamed {
+                    if let Some(old) = delta.old_file().path() {
+                        renamed.insert(old.to_string_lossy().replace('\\', "/"), current.clone());
+                    }
+                }
+                *churn.changes.entry(current).or_default() += 1;
+            }
+        }
+
+        Ok(churn)
+    }
+}
@@ -0,0 +1,353 @@
+//! Test, script and production code classification.
+//!
+//! Every file is one of three kinds:
+//!
+//! - `test` — test sources, fixtures and helpers: files under a `test`,
+//!   `tests`, `__tests__`, `spec` or `testdata` directory, files named like
+//!   tests (`test_*.py`, `*_test.go`, `*.test.ts`, `FooTest.java`,
+//!   `*_spec.rb`, `conftest.py`), and files whose content uses a test
+//!   framework (pytest/unittest tests, a Rust file of `#[test]` functions
+//!   outside `#[cfg(test)]`, jest/vitest/mocha `describe`/`it` with
+//!   `expect`, JUnit/TestNG/kotlin.test, RSpec/Minitest, PHPUnit,
+//!   xUnit/NUnit/MSTest, XCTest)
+//! - `script` — build and maintenance scripts: files under `scripts/`,
+//!   shell scripts, `setup.py`, `build.rs` and the like, and files starting
+//!   with a shebang
+//! - `production` — everything else
+//!
+//! `[files] test_paths` and `script_paths` globs take precedence over the
+//! built-in conventions. Reviews record the kind in
+//! [`Finding::code_kind`], and `[files.max_severity]` caps the severity of
+//! findings per kind. Graph `File` nodes carry the built-in classification.
+
+use std::collections::HashMap;
+use std::path::{Component, Path};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::config::FilesConfig;
+use crate::{Finding, Severity};
+
+/// What a file is for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum CodeKind {
+    #[default]
+    Production,
+    Test,
+    Script,
+}
+
+impl CodeKind {
+    /// Every kind, in the order `--only` and the docs list them
+    pub const ALL: [CodeKind; 3] = [Self::Production, Self::Test, Self::Script];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "production" => Some(Self::Production),
+            "test" => Some(Self::Test),
+            "script" => Some(Self::Script),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Production => "production",
+            Self::Test => "test",
+            Self::Script => "script",
+        }
+    }
+
+    pub fn is_production(&self) -> bool {
+        *self == Self::Production
+    }
+}
+
+impl std::fmt::Display for CodeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Directories whose contents are test code (compared lower-cased)
+const TEST_DIRS: &[&str] = &[
+    "test",
+    "tests",
+    "__tests__",
+    "__test__",
+    "__mocks__",
+    "spec",
+    "specs",
+    "testdata",
+    "e2e",
+    "androidtest",
+    "testfixtures",
+];
+
+/// Directories whose contents are scripts
+const SCRIPT_DIRS: &[&str] = &["scripts", "script"];
+
+/// File names of build and task scripts
+const SCRIPT_FILES: &[&str] = &[
+    "setup.py",
+    "noxfile.py",
+    "fabfile.py",
+    "manage.py",
+    "build.rs",
+    "rakefile",
+    "gruntfile.js",
+    "gulpfile.js",
+    "jakefile.js",
+];
+
+/// Extensions of shell and batch scripts
+const SCRIPT_EXTENSIONS: &[&str] = &["sh", "bash", "zsh", "fish", "ps1", "bat", "cmd"];
+
+/// Built-in classification of `path` with contents `source`. The path is
+/// taken relative to `root` when it is under it, so the directories the
+/// repository itself sits in do not count.
+pub fn classify(path: &Path, root: &Path, source: &str) -> CodeKind {
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    if let Some(kind) = by_path(rel) {
+        return kind;
+    }
+    if has_test_content(rel, source) {
+        return CodeKind::Test;
+    }
+    if source.starts_with("#!") {
+        return CodeKind::Script;
+    }
+    CodeKind::Production
+}
+
+/// Classification from the path alone, when it decides
+fn by_path(rel: &Path) -> Option<CodeKind> {
+    let dirs: Vec<String> = rel
+        .parent()
+        .into_iter()
+        .flat_map(Path::components)
+        .filter_map(|c| match c {
+            Component::Normal(s) => Some(s.to_string_lossy().to_lowercase()),
+            _ => None,
+        })
+        .collect();
+    if dirs.iter().any(|d| TEST_DIRS.contains(&d.as_str())) || is_test_name(rel) {
+        return Some(CodeKind::Test);
+    }
+    let name = rel.file_name()?.to_string_lossy().to_lowercase();
+    let ext = rel
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if dirs.iter().any(|d| SCRIPT_DIRS.contains(&d.as_str()))
+        || SCRIPT_FILES.contains(&name.as_str())
+        || SCRIPT_EXTENSIONS.contains(&ext.as_str())
+    {
+        return Some(CodeKind::Script);
+    }
+    None
+}
+
+/// Whether the file name follows a test naming convention
+fn is_test_name(rel: &Path) -> bool {
+    let Some(name) = rel.file_name().map(|n| n.to_string_lossy()) else {
+        return false;
+    };
+    let mut parts = name.split('.');
+    let stem = parts.next().unwrap_or("");
+    let rest: Vec<&str> = parts.collect();
+    // foo.test.ts, foo.spec.js, login.e2e.ts, form.cy.tsx
+    if rest.len() >= 2
+        && rest[..rest.len() - 1]
+            .iter()
+            .any(|p| matches!(*p, "test" | "spec" | "e2e" | "cy"))
+    {
+        return true;
+    }
+    let lower = stem.to_lowercase();
+    if matches!(lower.as_str(), "conftest" | "test" | "tests")
+        || lower.starts_with("test_")
+        || ["_test", "_tests", "_spec", "_unittest"]
+            .iter()
+            .any(|s| lower.ends_with(s))
+    {
+        return true;
+    }
+    // JUnit, NUnit, XCTest, PHPUnit: FooTest, FooTests, FooSpec, TestFoo
+    let jvm_like = rest.last().is_some_and(|ext| {
+        matches!(
+            *ext,
+            "java" | "kt" | "kts" | "scala" | "groovy" | "cs" | "swift" | "php"
+        )
+    });
+    jvm_like
+        && (["Test", "Tests", "Spec"].iter().any(|s| stem.ends_with(s))
+            || stem
+                .strip_prefix("Test")
+                .is_some_and(|r| r.starts_with(|c: char| c.is_ascii_uppercase())))
+}
+
+/// Whether `source` uses a test framework of its language
+fn has_test_content(rel: &Path, source: &str) -> bool {
+    let ext = rel
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let all = |patterns: &[&Regex]| patterns.iter().all(|re| re.is_match(source));
+    let s = signals();
+    match ext.as_str() {
+        "py" => all(&[&s.py_import, &s.py_test]),
+        "rs" => s.rs_test.is_match(source) && !source.contains("#[cfg(test)]"),
+        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => {
+            all(&[&s.js_block, &s.js_expect])
+        }
+        "java" | "kt" | "kts" | "scala" | "groovy" => all(&[&s.jvm_import, &s.jvm_test]),
+        "rb" => s.ruby.is_match(source),
+        "php" => s.php.is_match(source),
+        "cs" => s.csharp.is_match(source),
+        "swift" => s.swift.is_match(source),
+        _ => false,
+    }
+}
+
+/// Content signals of test frameworks
+struct Signals {
+    py_import: Regex,
+    py_test: Regex,
+    rs_test: Regex,
+    js_block: Regex,
+    js_expect: Regex,
+    jvm_import: Regex,
+    jvm_test: Regex,
+    ruby: Regex,
+    php: Regex,
+    csharp: Regex,
+    swift: Regex,
+}
+
+fn signals() -> &'static Signals {
+    static SIGNALS: OnceLock<Signals> = OnceLock::new();
+    SIGNALS.get_or_init(|| Signals {
+        py_import: Regex::new(r"(?m)^\s*(?:import|from)\s+(?:pytest|unittest)\b").unwrap(),
+        py_test: Regex::new(
+            r"(?m)^\s*(?:(?:async\s+)?def\s+test_?\w*\s*\(|class\s+\w+\(.*TestCase\s*\))",
+        )
+        .unwrap(),
+        rs_test: Regex::new(r"(?m)^\s*#\[(?:\w+::)?test\]").unwrap(),
+        js_block: Regex::new(r#"(?m)^\s*(?:describe|it|test)(?:\.\w+)?\s*\(\s*['"`]"#).unwrap(),
+        js_expect: Regex::new(r"\b(?:expect|assert)\s*[(.]").unwrap(),
+        jvm_import: Regex::new(
+            r"(?m)^\s*import\s+(?:static\s+)?(?:org\.junit|org\.testng|kotlin\.test|io\.kotest|spock\.lang)\b",
+        )
+        .unwrap(),
+        jvm_test: Regex::new(r"@(?:Test|ParameterizedTest)\b|:\s*(?:\w+Spec|Specification)\b")
+            .unwrap(),
+        ruby: Regex::new(
+            r#"(?m)^\s*(?:RSpec\.describe\b|require\s+['"](?:rspec|minitest/autorun|test/unit)['"]|class\s+\w+\s*<\s*(?:Minitest::Test|Test::Unit::TestCase|ActiveSupport::TestCase))"#,
+        )
+        .unwrap(),
+        php: Regex::new(r"(?m)^\s*(?:use\s+PHPUnit\\|(?:final\s+)?class\s+\w+\s+extends\s+\\?(?:PHPUnit\\Framework\\)?TestCase\b)")
+            .unwrap(),
+        csharp: Regex::new(
+            r"(?m)^\s*using\s+(?:Xunit|NUnit\.Framework|Microsoft\.VisualStudio\.TestTools\.UnitTesting)\s*;",
+        )
+        .unwrap(),
+        swift: Regex::new(r"(?m)^\s*(?:@testable\s+)?import\s+(?:XCTest|Testing|Quick)\b").unwrap(),
+    })
+}
+
+/// Classifier built from `[files]`: configured globs, then the built-in
+/// conventions, plus the per-kind severity caps
+#[derive(Debug, Clone, Default)]
+pub struct CodeKinds {
+    test_paths: Vec<glob::Pattern>,
+    script_paths: Vec<glob::Pattern>,
+    max_severity: HashMap<CodeKind, Severity>,
+}
+
+impl CodeKinds {
+    /// Invalid globs, kinds and severities are reported by
+    /// [`RevetConfig::validate`](crate::RevetConfig::validate); here they
+    /// are ignored.
+    pub fn from_config(config: &FilesConfig) -> Self {
+        let globs = |patterns: &[String]| {
+            patterns
+                .iter()
+                .filter_map(|p| glob::Pattern::new(p).ok())
+                .collect()
+        };
+        Self {
+            test_paths: globs(&config.test_paths),
+            script_paths: globs(&config.script_paths),
+            max_severity: config
+                .max_severity
+                .iter()
+                .filter_map(|(kind, severity)| {
+                    Some((CodeKind::parse(kind)?, parse_severity(severity)?))
+                })
+                .collect(),
+        }
+    }
+
+    /// Kind of `path` with contents `source`
+    pub fn classify(&self, path: &Path, root: &Path, source: &str) -> CodeKind {
+        let rel = path.strip_prefix(root).unwrap_or(path);
+        let rel_str = rel.to_string_lossy().replace('\\', "/");
+        if self.test_paths.iter().any(|p| p.matches(&rel_str)) {
+            CodeKind::Test
+        } else if self.script_paths.iter().any(|p| p.matches(&rel_str)) {
+            CodeKind::Script
+        } else {
+            classify(path, root, source)
+        }
+    }
+
+    /// Record the kind of each finding's file in [`Finding::code_kind`] and
+    /// cap its severity at `[files.max_severity]`. Each file is read once;
+    /// files that cannot be read are classified by path.
+    pub fn apply(&self, findings: Vec<Finding>, repo_root: &Path) -> Vec<Finding> {
+        let mut kinds: HashMap<std::path::PathBuf, CodeKind> = HashMap::new();
+        findings
+            .into_iter()
+            .map(|mut f| {
+                let kind = *kinds.entry(f.file.clone()).or_insert_with(|| {
+                    let abs = if f.file.is_relative() {
+                        repo_root.join(&f.file)
+                    } else {
+                        f.file.clone()
+                    };
+                    let source = std::fs::read_to_string(&abs).unwrap_or_default();
+                    self.classify(&abs, repo_root, &source)
+                });
+                f.code_kind = kind;
+                if let Some(&cap) = self.max_severity.get(&kind) {
+                    if severity_rank(f.severity) > severity_rank(cap) {
+                        f.severity = cap;
+                    }
+                }
+                f
+            })
+            .collect()
+    }
+}
+
+fn parse_severity(s: &str) -> Option<Severity> {
+    match s {
+        "error" => Some(Severity::Error),
+        "warning" => Some(Severity::Warning),
+        "info" => Some(Severity::Info),
+        _ => None,
+    }
+}
+
+fn severity_rank(s: Severity) -> u8 {
+    match s {
+        Severity::Error => 2,
+        Severity::Warning => 1,
+        Severity::Info => 0,
+    }
+}
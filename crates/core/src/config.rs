@@ -1,9 +1,11 @@
 //! Configuration file parsing for .revet.toml
 
+use crate::finding::{Confidence, Severity};
+use crate::profile::Scope;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 /// A user-defined regex-based rule in `.revet.toml`
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +14,11 @@ pub struct CustomRule {
     #[serde(default)]
     pub id: Option<String>,
 
+    /// Finding ID prefix instead of `CUSTOM` (e.g., "ACME" for `ACME-001`);
+    /// built-in prefixes are reserved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+
     /// Regex pattern (Rust `regex` crate syntax)
     pub pattern: String,
 
@@ -43,6 +50,106 @@ pub struct CustomRule {
     pub fix_replace: Option<String>,
 }
 
+/// A governance policy (`[[policy]]`) evaluated over the final findings
+///
+/// ```toml
+/// [[policy]]
+/// name = "no-new-payment-errors"
+/// filter = 'severity == error and path ~ "payments/**"'
+/// aggregate = "exists"
+/// ```
+///
+/// A policy is violated when the aggregate over the matching findings
+/// satisfies the comparison: `count > 0` by default, `exists` when any
+/// finding matches. See [`crate::policy`] for the filter language.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyConfig {
+    /// Unique name, shown in the report and used by `--explain-policy`
+    pub name: String,
+
+    /// Optional explanation shown next to a violated policy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Filter expression over finding fields
+    pub filter: String,
+
+    /// "count" (default) or "exists"
+    #[serde(default = "default_aggregate")]
+    pub aggregate: String,
+
+    /// ">" (default), ">=", "<", "<=", "==" or "!="; `count` only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comparator: Option<String>,
+
+    /// Right-hand side of the comparison (default 0); `count` only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<usize>,
+
+    /// "fail" (default), "warn" or "require-escalation"
+    #[serde(default = "default_policy_action")]
+    pub action: String,
+}
+
+/// Third-party code vendored with local patches (`[[vendored]]`)
+///
+/// ```toml
+/// [[vendored]]
+/// path = "third_party/libfoo"
+/// upstream_ref = "vendor/libfoo-1.4.2"
+/// fallback = "info-only"
+/// ```
+///
+/// Findings under `path` are reported only on the lines that differ from
+/// `upstream_ref`, in every review mode. See [`crate::vendored`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VendoredConfig {
+    /// Vendored directory, relative to the repository root
+    pub path: String,
+
+    /// What the directory was vendored from: a branch, tag or commit, or
+    /// the tree id recorded when vendoring (`git rev-parse HEAD:<path>`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_ref: Option<String>,
+
+    /// What to do when `upstream_ref` is missing or does not resolve:
+    /// `skip`, `info-only` or `analyze`, as for generated files; defaults
+    /// to `[files] generated`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<String>,
+}
+
+/// A project of a monorepo and the projects it depends on
+/// (`[[project]]`)
+///
+/// ```toml
+/// [[project]]
+/// path = "packages/api"
+/// depends_on = ["packages/shared"]
+/// ```
+///
+/// Directories holding a manifest (`package.json`, `Cargo.toml`, ..) are
+/// projects without an entry; one is needed to declare dependencies, whose
+/// files are then part of `revet hash-inputs --project`. See
+/// [`crate::hash_inputs`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    /// Project directory, relative to the repository root
+    pub path: String,
+
+    /// Directories of the projects it depends on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+}
+
+fn default_aggregate() -> String {
+    "count".to_string()
+}
+
+fn default_policy_action() -> String {
+    "fail".to_string()
+}
+
 fn default_warning() -> String {
     "warning".to_string()
 }
@@ -93,401 +200,3837 @@ impl GateConfig {
     }
 }
 
-/// Main configuration structure for .revet.toml
+/// Risk score label thresholds and critical paths (`[risk]`)
+///
+/// ```toml
+/// [risk]
+/// medium = 30                          # score ≥ 30 is "medium"
+/// high   = 60                          # score ≥ 60 is "high"
+/// critical_paths = ["src/billing/**"]  # dependents here add to the score
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RevetConfig {
-    #[serde(default)]
-    pub general: GeneralConfig,
+pub struct RiskConfig {
+    /// Lowest score labelled medium
+    #[serde(default = "default_risk_medium")]
+    pub medium: u8,
 
-    #[serde(default)]
-    pub modules: ModulesConfig,
-
-    #[serde(default)]
-    pub ai: AIConfig,
-
-    #[serde(default)]
-    pub ignore: IgnoreConfig,
+    /// Lowest score labelled high
+    #[serde(default = "default_risk_high")]
+    pub high: u8,
 
+    /// Globs (relative to the repo root) marking critical code
     #[serde(default)]
-    pub output: OutputConfig,
-
-    /// Quality gate: per-severity maximum finding counts
-    #[serde(default)]
-    pub gate: GateConfig,
-
-    /// User-defined custom rules
-    #[serde(default, rename = "rules")]
-    pub rules: Vec<CustomRule>,
+    pub critical_paths: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GeneralConfig {
-    /// Languages to analyze (auto-detected if empty)
-    #[serde(default)]
-    pub languages: Vec<String>,
+fn default_risk_medium() -> u8 {
+    30
+}
 
-    /// Default diff base
-    #[serde(default = "default_diff_base")]
-    pub diff_base: String,
+fn default_risk_high() -> u8 {
+    60
+}
 
-    /// Severity threshold for non-zero exit code
-    #[serde(default = "default_fail_on")]
-    pub fail_on: String,
+impl Default for RiskConfig {
+    fn default() -> Self {
+        Self {
+            medium: default_risk_medium(),
+            high: default_risk_high(),
+            critical_paths: Vec::new(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ModulesConfig {
-    /// Enable/disable domain modules
-    #[serde(default = "default_true")]
-    pub ml: bool,
+/// Weights of the review effort estimate (`[review_estimate]`)
+///
+/// Each factor adds `value × weight` minutes; the total picks the band.
+///
+/// ```toml
+/// [review_estimate]
+/// line_minutes      = 0.2            # per changed line, weighted by complexity
+/// complexity_weight = 0.1            # a line in a function of complexity c weighs 1 + 0.1 × (c − 1)
+/// file_minutes      = 2.0            # per changed file
+/// project_minutes   = 5.0            # per distinct project touched
+/// risk_minutes      = 0.3            # per point of the PR risk score (0–100)
+/// error_minutes     = 5.0            # per new error finding
+/// warning_minutes   = 2.0            # per new warning
+/// info_minutes      = 0.5            # per new info finding
+/// bands             = [15, 60, 180]  # minutes where M, L and XL start
+/// max_reviewers     = 3
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewEstimateConfig {
+    #[serde(default = "default_line_minutes")]
+    pub line_minutes: f64,
+    #[serde(default = "default_complexity_weight")]
+    pub complexity_weight: f64,
+    #[serde(default = "default_file_minutes")]
+    pub file_minutes: f64,
+    #[serde(default = "default_project_minutes")]
+    pub project_minutes: f64,
+    #[serde(default = "default_risk_minutes")]
+    pub risk_minutes: f64,
+    #[serde(default = "default_error_minutes")]
+    pub error_minutes: f64,
+    #[serde(default = "default_warning_minutes")]
+    pub warning_minutes: f64,
+    #[serde(default = "default_info_minutes")]
+    pub info_minutes: f64,
+    /// Minutes at which the M, L and XL bands start
+    #[serde(default = "default_bands")]
+    pub bands: Vec<f64>,
+    /// Reviewers suggested at most
+    #[serde(default = "default_max_reviewers")]
+    pub max_reviewers: usize,
+}
 
-    #[serde(default = "default_true")]
-    pub security: bool,
+fn default_line_minutes() -> f64 {
+    0.2
+}
 
-    #[serde(default)]
-    pub infra: bool,
+fn default_complexity_weight() -> f64 {
+    0.1
+}
 
-    #[serde(default)]
-    pub react: bool,
+fn default_file_minutes() -> f64 {
+    2.0
+}
 
-    #[serde(default)]
-    pub async_patterns: bool,
+fn default_project_minutes() -> f64 {
+    5.0
+}
 
-    #[serde(default)]
-    pub dependency: bool,
+fn default_risk_minutes() -> f64 {
+    0.3
+}
 
-    #[serde(default)]
-    pub error_handling: bool,
+fn default_error_minutes() -> f64 {
+    5.0
+}
 
-    /// Detect unused exported symbols (opt-in, can be noisy)
-    #[serde(default)]
-    pub dead_code: bool,
+fn default_warning_minutes() -> f64 {
+    2.0
+}
 
-    /// Detect circular import chains (default on)
-    #[serde(default = "default_true")]
-    pub cycles: bool,
+fn default_info_minutes() -> f64 {
+    0.5
+}
 
-    /// Detect overly complex functions: length, parameter count, cyclomatic complexity, nesting
-    #[serde(default)]
-    pub complexity: bool,
+fn default_bands() -> Vec<f64> {
+    vec![15.0, 60.0, 180.0]
+}
 
-    /// Cyclomatic complexity threshold — warn above this value (default: 10, error at 2x)
-    #[serde(default = "default_complexity_threshold")]
-    pub complexity_threshold: usize,
+fn default_max_reviewers() -> usize {
+    3
+}
 
-    /// Detect imported symbols that are never used within the same file
-    #[serde(default)]
-    pub dead_imports: bool,
+impl Default for ReviewEstimateConfig {
+    fn default() -> Self {
+        Self {
+            line_minutes: default_line_minutes(),
+            complexity_weight: default_complexity_weight(),
+            file_minutes: default_file_minutes(),
+            project_minutes: default_project_minutes(),
+            risk_minutes: default_risk_minutes(),
+            error_minutes: default_error_minutes(),
+            warning_minutes: default_warning_minutes(),
+            info_minutes: default_info_minutes(),
+            bands: default_bands(),
+            max_reviewers: default_max_reviewers(),
+        }
+    }
+}
 
-    /// Detect tools invoked in CI/scripts that are not declared in any manifest
-    #[serde(default)]
-    pub toolchain: bool,
+/// Target runtime versions for the `UPGRADE` analyzer (`[upgrade]`)
+///
+/// ```toml
+/// [upgrade]
+/// python = "3.12"
+/// node   = "20"
+/// packs  = [".revet/upgrade/python.toml"]  # replace or add knowledge packs
+/// ```
+///
+/// A runtime without a target here takes the project's language target
+/// (see [`crate::language_target`]): `[targets]`, or the lower bound of the
+/// nearest `pyproject.toml` `requires-python` or `package.json` `engines`
+/// entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeConfig {
+    /// Knowledge pack files, relative to the repository root. A pack for
+    /// a runtime revet bundles replaces the bundled one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packs: Vec<String>,
 
-    /// Detect hardcoded IP addresses and production/staging URLs in source code
-    #[serde(default)]
-    pub hardcoded_endpoints: bool,
+    /// Target version per runtime, keyed by the pack's `runtime`
+    #[serde(flatten)]
+    pub targets: BTreeMap<String, String>,
+}
 
-    /// Detect unnamed numeric literals (magic numbers) that should be named constants
-    #[serde(default)]
-    pub magic_numbers: bool,
+/// Per-file analysis time warnings, the memory ceiling and the in-memory
+/// analyzer memo of watch and daemon sessions (`[performance]`)
+///
+/// ```toml
+/// [performance]
+/// slow_file_warn_ms = 500
+/// max_memory_mb = 2048
+/// memo_mb = 128        # 0 turns the memo off
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PerformanceConfig {
+    /// Report an Info finding (`PERF`) for each file an analyzer spends at
+    /// least this long on. Turns on per-file timing for every review.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slow_file_warn_ms: Option<u64>,
 
-    /// Detect public functions/classes with no mention in any test file
-    #[serde(default)]
-    pub test_coverage: bool,
+    /// Soft memory ceiling of a review, in MB. Past 85% of it the review
+    /// sheds load; still over it, the review stops with the findings so far
+    /// (see [`crate::memory`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory_mb: Option<u64>,
 
-    /// Detect copy-paste duplicate code blocks across files
-    #[serde(default)]
-    pub duplication: bool,
+    /// Memory, in MB, a long-lived `revet watch` or `revet daemon` session
+    /// keeps analyzer results in, so that unchanged files are not analyzed
+    /// again (see [`crate::AnalysisMemo`]). Defaults to
+    /// [`DEFAULT_MEMO_MB`](Self::DEFAULT_MEMO_MB); `0` turns the memo off.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo_mb: Option<u64>,
+}
 
-    /// Minimum block size (lines) to consider as a duplicate (default: 6)
-    #[serde(default = "default_duplication_min_lines")]
-    pub duplication_min_lines: usize,
+impl PerformanceConfig {
+    /// `memo_mb` when it is not set
+    pub const DEFAULT_MEMO_MB: u64 = 256;
 
-    /// Maximum transitive call-graph depth for impact analysis (default: 3)
-    #[serde(default = "default_call_graph_depth")]
-    pub call_graph_depth: usize,
+    /// `slow_file_warn_ms` as a duration
+    pub fn slow_file_warn(&self) -> Option<std::time::Duration> {
+        self.slow_file_warn_ms.map(std::time::Duration::from_millis)
+    }
 
-    /// Module-specific configurations
-    #[serde(flatten)]
-    pub module_configs: HashMap<String, toml::Value>,
+    /// Capacity of the analyzer memo in bytes, `0` when it is off
+    pub fn memo_bytes(&self) -> usize {
+        let mb = self.memo_mb.unwrap_or(Self::DEFAULT_MEMO_MB);
+        usize::try_from(mb.saturating_mul(1024 * 1024)).unwrap_or(usize::MAX)
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AIConfig {
-    /// LLM provider: "anthropic" | "openai" | "ollama"
-    #[serde(default = "default_provider")]
-    pub provider: String,
-
-    /// Model name
-    #[serde(default = "default_model")]
-    pub model: String,
+/// Findings budget `revet calibrate` suggests thresholds for
+/// (`[calibration]`)
+///
+/// ```toml
+/// [calibration]
+/// percentile = 95      # flag at most the top 5% of each distribution
+/// max_findings = 20    # or: at most 20 findings per rule (wins when set)
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalibrationConfig {
+    /// Suggest thresholds that flag at most the items above this
+    /// percentile of each metric (1–99)
+    #[serde(default = "default_calibration_percentile")]
+    pub percentile: u8,
 
-    /// API key — can also be set via ANTHROPIC_API_KEY / OPENAI_API_KEY env var.
-    /// Not required for Ollama.
+    /// Suggest thresholds that leave at most this many findings per rule,
+    /// instead of a percentile
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub api_key: Option<String>,
+    pub max_findings: Option<usize>,
+}
 
-    /// Max cost per run in USD
-    #[serde(default = "default_max_cost")]
-    pub max_cost_per_run: f64,
+fn default_calibration_percentile() -> u8 {
+    95
+}
 
-    /// Base URL for the LLM API. Defaults to the provider's standard endpoint.
-    /// Set this to point Ollama at a non-default host/port, e.g. "http://10.0.0.5:11434".
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub base_url: Option<String>,
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            percentile: default_calibration_percentile(),
+            max_findings: None,
+        }
+    }
 }
 
+/// Issue tracker sync settings (`[issues]`, `revet issues sync`)
+///
+/// ```toml
+/// [issues]
+/// branch = "main"          # runs that count (default: [general] diff_base)
+/// min_severity = "error"   # least severe finding tracked
+/// min_age_runs = 3         # consecutive full runs a finding must survive
+/// rules = ["SEC", "SQL"]   # prefixes, modules or module:rule; empty = all
+/// label = "revet"          # label of the issues revet manages
+///
+/// [issues.github]
+/// repository = "acme/api"  # default: GITHUB_REPOSITORY
+///
+/// [issues.jira]
+/// url = "https://acme.atlassian.net"
+/// project = "SEC"
+/// issue_type = "Task"
+/// done_transition = "Done"
+/// ```
+///
+/// Credentials come from the environment only: `GITHUB_TOKEN`, or
+/// `JIRA_EMAIL` and `JIRA_API_TOKEN`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct IgnoreConfig {
-    /// Paths to ignore entirely (glob patterns)
-    #[serde(default = "default_ignore_paths")]
-    pub paths: Vec<String>,
-
-    /// Finding ID prefixes to suppress globally (e.g. ["SEC", "SQL"])
-    #[serde(default)]
-    pub findings: Vec<String>,
+pub struct IssuesConfig {
+    /// Branch whose full runs count; `None` for `[general] diff_base`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
 
-    /// Per-path rule suppression: glob pattern → list of finding ID prefixes
-    ///
-    /// Example in .revet.toml:
-    /// ```toml
-    /// [ignore.per_path]
-    /// "**/tests/**" = ["SEC", "SQL"]
-    /// "src/generated/**" = ["*"]
-    /// ```
-    #[serde(default)]
-    pub per_path: std::collections::HashMap<String, Vec<String>>,
-}
+    /// Least severe finding tracked: "error", "warning" or "info"
+    #[serde(default = "default_issues_min_severity")]
+    pub min_severity: String,
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct OutputConfig {
-    /// Default output format
-    #[serde(default = "default_format")]
-    pub format: String,
+    /// Consecutive full runs on the branch a finding must survive
+    #[serde(default = "default_issues_min_age_runs")]
+    pub min_age_runs: usize,
 
-    /// Enable color output
-    #[serde(default = "default_true")]
-    pub color: bool,
+    /// Only findings of these rules (an ID prefix, module or
+    /// `module:rule`, as in a `revet-ignore` comment); empty for all
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<String>,
 
-    /// Show evidence snippets
-    #[serde(default = "default_true")]
-    pub show_evidence: bool,
+    /// Label put on created issues and used to find them again
+    #[serde(default = "default_issues_label")]
+    pub label: String,
 
-    /// Max findings to display (0 = unlimited)
     #[serde(default)]
-    pub max_findings: usize,
-}
+    pub github: GithubIssuesConfig,
 
-// Default functions
-fn default_diff_base() -> String {
-    "main".to_string()
+    #[serde(default)]
+    pub jira: JiraIssuesConfig,
 }
 
-fn default_fail_on() -> String {
+fn default_issues_min_severity() -> String {
     "error".to_string()
 }
 
-fn default_complexity_threshold() -> usize {
-    10
+fn default_issues_min_age_runs() -> usize {
+    3
 }
 
-fn default_duplication_min_lines() -> usize {
-    6
+fn default_issues_label() -> String {
+    "revet".to_string()
 }
 
-fn default_call_graph_depth() -> usize {
-    3
-}
+impl Default for IssuesConfig {
+    fn default() -> Self {
+        Self {
+            branch: None,
+            min_severity: default_issues_min_severity(),
+            min_age_runs: default_issues_min_age_runs(),
+            rules: Vec::new(),
+            label: default_issues_label(),
+            github: GithubIssuesConfig::default(),
+            jira: JiraIssuesConfig::default(),
+        }
+    }
+}
 
-fn default_true() -> bool {
-    true
+/// Baseline delta events (`[reporting]`)
+///
+/// After `revet baseline`, `revet baseline review` and `--fix` runs that
+/// change the baseline, the entries added, fixed and expired per owning
+/// team are POSTed to `webhook_url` as JSON, signed with HMAC-SHA256 of the
+/// body under the secret in `webhook_secret_env`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportingConfig {
+    /// `https://` (or `http://`) endpoint; unset sends nothing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+
+    /// Environment variable holding the signing secret
+    #[serde(default = "default_webhook_secret_env")]
+    pub webhook_secret_env: String,
+
+    /// Retries of a delivery that failed or was answered 5xx or 429
+    #[serde(default = "default_webhook_retries")]
+    pub webhook_retries: u32,
+
+    /// Wait before the first retry, in milliseconds; doubled for each one
+    #[serde(default = "default_webhook_backoff_ms")]
+    pub webhook_backoff_ms: u64,
+
+    /// Timeout of each delivery, in milliseconds
+    #[serde(default = "default_webhook_timeout_ms")]
+    pub webhook_timeout_ms: u64,
 }
 
-fn default_provider() -> String {
-    "anthropic".to_string()
+fn default_webhook_secret_env() -> String {
+    "REVET_WEBHOOK_SECRET".to_string()
 }
 
-fn default_model() -> String {
-    "claude-sonnet-4-20250514".to_string()
+fn default_webhook_retries() -> u32 {
+    3
 }
 
-fn default_max_cost() -> f64 {
-    1.0
+fn default_webhook_backoff_ms() -> u64 {
+    1000
 }
 
-fn default_ignore_paths() -> Vec<String> {
-    vec![
-        "vendor/".to_string(),
-        "node_modules/".to_string(),
-        "dist/".to_string(),
-        ".git/".to_string(),
-        "__pycache__/".to_string(),
-        ".venv/".to_string(),
-        "venv/".to_string(),
-        "env/".to_string(),
-        "site-packages/".to_string(),
-        "build/".to_string(),
-        "target/".to_string(),
-        ".tox/".to_string(),
-        ".eggs/".to_string(),
-        ".mypy_cache/".to_string(),
-        ".pytest_cache/".to_string(),
-        ".revet-cache/".to_string(),
-    ]
+fn default_webhook_timeout_ms() -> u64 {
+    5000
 }
 
-fn default_format() -> String {
-    "terminal".to_string()
+impl Default for ReportingConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            webhook_secret_env: default_webhook_secret_env(),
+            webhook_retries: default_webhook_retries(),
+            webhook_backoff_ms: default_webhook_backoff_ms(),
+            webhook_timeout_ms: default_webhook_timeout_ms(),
+        }
+    }
 }
 
-impl Default for RevetConfig {
+/// GitHub Issues target (`[issues.github]`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GithubIssuesConfig {
+    /// `owner/repo`; `None` for `GITHUB_REPOSITORY`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+}
+
+/// Jira target (`[issues.jira]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraIssuesConfig {
+    /// Site URL, e.g. `https://acme.atlassian.net`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Project key issues are created in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+
+    /// Issue type of created issues
+    #[serde(default = "default_jira_issue_type")]
+    pub issue_type: String,
+
+    /// Name of the transition that resolves an issue
+    #[serde(default = "default_jira_done_transition")]
+    pub done_transition: String,
+}
+
+fn default_jira_issue_type() -> String {
+    "Task".to_string()
+}
+
+fn default_jira_done_transition() -> String {
+    "Done".to_string()
+}
+
+impl Default for JiraIssuesConfig {
     fn default() -> Self {
-        toml::from_str("").expect("empty TOML should parse to defaults")
+        Self {
+            url: None,
+            project: None,
+            issue_type: default_jira_issue_type(),
+            done_transition: default_jira_done_transition(),
+        }
     }
 }
 
-impl Default for GeneralConfig {
+/// Graph cache settings (`[cache]`)
+///
+/// ```toml
+/// [cache]
+/// format = "compact"  # "msgpack" (default) or "compact"
+/// full_snapshot_every = 10
+/// keep_last = 30
+/// keep_days = 90
+///
+/// [cache.remote]
+/// url = "s3://ci-cache/revet"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// On-disk format of `.revet-cache/graph.*`. `compact` is a flat binary
+    /// layout that is memory-mapped on load, for very large graphs.
+    #[serde(default = "default_cache_format")]
+    pub format: String,
+
+    /// The graph store (`.revet-cache/graph.db`) writes every Nth snapshot
+    /// in full and only the changes since the previous snapshot in between.
+    /// 1 writes every snapshot in full.
+    #[serde(default = "default_full_snapshot_every")]
+    pub full_snapshot_every: usize,
+
+    /// `revet snapshot gc` keeps the N newest snapshots
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_last: Option<usize>,
+
+    /// `revet snapshot gc` keeps snapshots flushed within the last N days
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_days: Option<u64>,
+
+    /// Cache shared between machines, layered over the local one
+    #[serde(default)]
+    pub remote: RemoteCacheConfig,
+}
+
+/// Remote cache shared by CI jobs (`[cache.remote]`)
+///
+/// Per-file parse results and domain analyzer findings are read from and
+/// written to `url` as well as the local cache. Any remote error falls back
+/// to the local cache for the rest of the run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteCacheConfig {
+    /// `https://host/prefix` (or `http://`) for a server answering GET and
+    /// PUT of `<url>/<key>`, or `s3://bucket/prefix` in builds with the
+    /// `s3-cache` feature. Unset disables the remote cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Environment variable holding a bearer token for HTTP servers
+    #[serde(default = "default_remote_token_env")]
+    pub token_env: String,
+
+    /// Timeout of each remote request, in milliseconds
+    #[serde(default = "default_remote_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_remote_token_env() -> String {
+    "REVET_CACHE_TOKEN".to_string()
+}
+
+fn default_remote_timeout_ms() -> u64 {
+    5000
+}
+
+impl Default for RemoteCacheConfig {
     fn default() -> Self {
         Self {
-            languages: Vec::new(),
-            diff_base: default_diff_base(),
-            fail_on: default_fail_on(),
+            url: None,
+            token_env: default_remote_token_env(),
+            timeout_ms: default_remote_timeout_ms(),
         }
     }
 }
 
-impl Default for ModulesConfig {
+fn default_cache_format() -> String {
+    "msgpack".to_string()
+}
+
+fn default_full_snapshot_every() -> usize {
+    10
+}
+
+impl Default for CacheConfig {
     fn default() -> Self {
         Self {
-            ml: true,
-            security: true,
-            infra: false,
-            react: false,
-            async_patterns: false,
-            dependency: false,
-            error_handling: false,
-            dead_code: false,
-            cycles: true,
-            complexity: false,
-            complexity_threshold: 10,
-            dead_imports: false,
-            toolchain: false,
-            hardcoded_endpoints: false,
-            magic_numbers: false,
-            test_coverage: false,
-            duplication: false,
-            duplication_min_lines: default_duplication_min_lines(),
-            call_graph_depth: default_call_graph_depth(),
-            module_configs: HashMap::new(),
+            format: default_cache_format(),
+            full_snapshot_every: default_full_snapshot_every(),
+            keep_last: None,
+            keep_days: None,
+            remote: RemoteCacheConfig::default(),
+        }
+    }
+}
+
+/// Where revet writes its state (`[storage]`)
+///
+/// Without a data directory, state lives in `.revet-cache/` inside the
+/// repository. With one (or with `REVET_DATA_DIR`, which takes precedence),
+/// each repository gets its own subdirectory there, so nothing is written
+/// into the checkout.
+///
+/// ```toml
+/// [storage]
+/// data_dir = "/var/cache/revet"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// Directory holding one state directory per repository. Relative paths
+    /// are resolved against the repository root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_dir: Option<String>,
+}
+
+/// Objects a partial clone left out (`[git]`)
+///
+/// A blobless clone has every commit and tree but only the file contents
+/// it checked out. When a feature needs contents of another commit (the
+/// diff base, blame, the history secret scan), revet fetches the missing
+/// ones from the promisor remote in batches, up to `fetch_limit` objects
+/// per run. Past the limit, or with `allow_fetch = false`, the feature
+/// skips what is missing and the summary says so.
+///
+/// ```toml
+/// [git]
+/// allow_fetch = false   # CI: never touch the network
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitConfig {
+    /// Fetch missing objects from the promisor remote
+    #[serde(default = "default_true")]
+    pub allow_fetch: bool,
+
+    /// Most objects fetched in one run
+    #[serde(default = "default_fetch_limit")]
+    pub fetch_limit: usize,
+}
+
+fn default_fetch_limit() -> usize {
+    200
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            allow_fetch: true,
+            fetch_limit: default_fetch_limit(),
+        }
+    }
+}
+
+/// Baseline location and upkeep (`[baseline]`)
+///
+/// ```toml
+/// [baseline]
+/// path = "/var/lib/revet/acme-api-baseline.json"
+/// auto_reconcile = true
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BaselineConfig {
+    /// Baseline file, which may be outside the repository. Relative paths
+    /// are resolved against the repository root. Defaults to
+    /// `baseline.json` in the state directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// After `--fix`, move the anchor lines of the entries in the edited
+    /// files and drop those whose line a fix deleted, saving the baseline
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_reconcile: bool,
+}
+
+/// Generated-file handling and code-kind classification (`[files]`)
+///
+/// ```toml
+/// [files]
+/// generated = "info-only"  # "skip" (default), "info-only" or "analyze"
+/// generated_markers = ["^// Code generated .* DO NOT EDIT\\.$", "@generated"]
+/// marker_lines = 10
+/// test_paths = ["qa/**"]
+/// script_paths = ["tools/**"]
+///
+/// [files.max_severity]
+/// test = "warning"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilesConfig {
+    /// What review does with files carrying a generated-code marker:
+    /// `skip` reports no findings in them (they are still parsed, so other
+    /// files resolve their symbols), `info-only` downgrades their findings
+    /// to info and keeps them out of the fail-on gate, `analyze` treats them
+    /// like any other file
+    #[serde(default = "default_generated_policy")]
+    pub generated: String,
+
+    /// Regexes matched against each of a file's first `marker_lines` lines.
+    /// Setting this replaces the built-in list
+    /// ([`DEFAULT_MARKERS`](crate::generated::DEFAULT_MARKERS)).
+    #[serde(default = "default_generated_markers")]
+    pub generated_markers: Vec<String>,
+
+    #[serde(default = "default_marker_lines")]
+    pub marker_lines: usize,
+
+    /// Globs (relative to the repository root) of test code the built-in
+    /// conventions miss (see [`code_kind`](crate::code_kind))
+    #[serde(default)]
+    pub test_paths: Vec<String>,
+
+    /// Globs of build and maintenance scripts
+    #[serde(default)]
+    pub script_paths: Vec<String>,
+
+    /// Highest severity reported per code kind (`production`, `test`,
+    /// `script`)
+    #[serde(default)]
+    pub max_severity: BTreeMap<String, String>,
+}
+
+fn default_generated_policy() -> String {
+    "skip".to_string()
+}
+
+fn default_generated_markers() -> Vec<String> {
+    crate::generated::DEFAULT_MARKERS
+        .iter()
+        .map(|m| m.to_string())
+        .collect()
+}
+
+fn default_marker_lines() -> usize {
+    10
+}
+
+impl Default for FilesConfig {
+    fn default() -> Self {
+        Self {
+            generated: default_generated_policy(),
+            generated_markers: default_generated_markers(),
+            marker_lines: default_marker_lines(),
+            test_paths: Vec::new(),
+            script_paths: Vec::new(),
+            max_severity: BTreeMap::new(),
+        }
+    }
+}
+
+/// Main configuration structure for .revet.toml
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevetConfig {
+    #[serde(default)]
+    pub general: GeneralConfig,
+
+    #[serde(default)]
+    pub modules: ModulesConfig,
+
+    #[serde(default)]
+    pub ai: AIConfig,
+
+    #[serde(default)]
+    pub ignore: IgnoreConfig,
+
+    #[serde(default)]
+    pub output: OutputConfig,
+
+    /// Per-language parser settings
+    #[serde(default)]
+    pub parser: ParserConfig,
+
+    /// Extra configuration-sensitive call patterns for the `MAGIC` analyzer
+    #[serde(default)]
+    pub magic_numbers: MagicNumbersConfig,
+
+    /// Extra closeable types for the `RES` analyzer
+    #[serde(default)]
+    pub resource_leaks: ResourceLeaksConfig,
+
+    /// Sensitive-name list for the `LOG` analyzer
+    #[serde(default)]
+    pub sensitive_logging: SensitiveLoggingConfig,
+
+    /// Custom analysis profiles (`[profiles.<name>]`)
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileConfig>,
+
+    /// Rule switches for the `K8S` analyzer
+    #[serde(default)]
+    pub kubernetes: KubernetesConfig,
+
+    /// Configured analyzers (`[[analyzers.generated]]`)
+    #[serde(default)]
+    pub analyzers: AnalyzersConfig,
+
+    /// Severity per finding-ID prefix or finding ID (`[severity]`):
+    /// "error", "warning", "info", or "off" to drop the finding
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, String>,
+
+    /// Quality gate: per-severity maximum finding counts
+    #[serde(default)]
+    pub gate: GateConfig,
+
+    /// Risk scoring for changed symbols in diff mode
+    #[serde(default)]
+    pub risk: RiskConfig,
+
+    /// Review effort estimate and suggested reviewers in diff mode
+    #[serde(default)]
+    pub review_estimate: ReviewEstimateConfig,
+
+    /// Runtime versions the `UPGRADE` analyzer checks against
+    #[serde(default)]
+    pub upgrade: UpgradeConfig,
+
+    /// Language versions the project targets, per language key
+    /// (`ecmascript`, `node`, `python`, `rust`, `java`), overriding what
+    /// manifests declare (see [`crate::language_target`])
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub targets: BTreeMap<String, String>,
+
+    /// Graph cache settings
+    #[serde(default)]
+    pub cache: CacheConfig,
+
+    /// Where caches, run logs and the graph store are written
+    #[serde(default)]
+    pub storage: StorageConfig,
+
+    /// Fetching the objects a partial clone left out
+    #[serde(default)]
+    pub git: GitConfig,
+
+    /// Baseline file location
+    #[serde(default)]
+    pub baseline: BaselineConfig,
+
+    /// Per-file analysis time warnings
+    #[serde(default)]
+    pub performance: PerformanceConfig,
+
+    /// Findings budget for `revet calibrate`
+    #[serde(default)]
+    pub calibration: CalibrationConfig,
+
+    /// Issue tracker sync for `revet issues sync`
+    #[serde(default)]
+    pub issues: IssuesConfig,
+
+    /// Baseline delta events sent to a webhook
+    #[serde(default)]
+    pub reporting: ReportingConfig,
+
+    /// Generated-file detection and policy
+    #[serde(default)]
+    pub files: FilesConfig,
+
+    /// User-defined custom rules
+    #[serde(default, rename = "rules")]
+    pub rules: Vec<CustomRule>,
+
+    /// Governance policies over the final findings
+    #[serde(default, rename = "policy")]
+    pub policies: Vec<PolicyConfig>,
+
+    /// Vendored third-party directories with local patches
+    #[serde(default)]
+    pub vendored: Vec<VendoredConfig>,
+
+    /// Monorepo projects and their dependencies
+    #[serde(default, rename = "project")]
+    pub projects: Vec<ProjectConfig>,
+}
+
+/// A `[severity]` entry
+///
+/// ```toml
+/// [severity]
+/// SEC         = "error"    # every finding of a prefix
+/// CMPLX       = "info"
+/// "ASYNC-003" = "warning"  # one finding; wins over its prefix
+/// DOC         = "off"      # drop the findings
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeverityOverride {
+    Set(Severity),
+    Off,
+}
+
+impl SeverityOverride {
+    /// `"error"`, `"warning"`, `"info"` or `"off"`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "error" => Some(Self::Set(Severity::Error)),
+            "warning" => Some(Self::Set(Severity::Warning)),
+            "info" => Some(Self::Set(Severity::Info)),
+            "off" => Some(Self::Off),
+            _ => None,
+        }
+    }
+}
+
+/// Per-language parser settings (`[parser.<lang>]`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParserConfig {
+    #[serde(default)]
+    pub go: GoParserConfig,
+    #[serde(default)]
+    pub python: PythonParserConfig,
+    #[serde(default)]
+    pub csharp: CSharpParserConfig,
+}
+
+/// Go parser settings
+///
+/// ```toml
+/// [parser.go]
+/// platforms = ["linux/amd64"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GoParserConfig {
+    /// Target platforms as `"GOOS/GOARCH"`. When set, calls resolve to the
+    /// build-constrained variant matching these platforms and variants for
+    /// other platforms are skipped by graph analyzers. Empty = analyze all.
+    #[serde(default)]
+    pub platforms: Vec<String>,
+}
+
+/// Python parser settings
+///
+/// ```toml
+/// [parser.python.frameworks.jobs]
+/// decorators   = ["jobs.register", "*.scheduled"]
+/// dependencies = ["Inject"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PythonParserConfig {
+    /// Frameworks that call functions on the application's behalf, by name.
+    /// Added to the built-in `flask`, `fastapi`, `celery`, `click` and
+    /// `pytest` entries; an entry with a built-in name replaces it.
+    #[serde(default)]
+    pub frameworks: BTreeMap<String, PythonFrameworkConfig>,
+}
+
+/// One `[parser.python.frameworks.<name>]` entry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PythonFrameworkConfig {
+    /// Decorators (without `@` and arguments) that register the decorated
+    /// function with the framework; `*` matches any run of characters
+    #[serde(default)]
+    pub decorators: Vec<String>,
+
+    /// Callables whose first argument is a function the framework injects,
+    /// like FastAPI's `Depends(get_db)`
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+/// C# parser settings
+///
+/// ```toml
+/// [parser.csharp.generated.validation]
+/// attributes = ["GenerateValidator"]
+/// members    = ["Validate{Name}"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CSharpParserConfig {
+    /// Source generators whose members exist only in generated code, by
+    /// name. Added to the built-in `regex`, `logging`, `mvvm_property`,
+    /// `mvvm_command` and `grpc` entries; an entry with a built-in name
+    /// replaces it.
+    #[serde(default)]
+    pub generated: BTreeMap<String, CSharpGeneratorConfig>,
+}
+
+/// One `[parser.csharp.generated.<name>]` entry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CSharpGeneratorConfig {
+    /// Attributes (without brackets or arguments) that trigger the
+    /// generator on the member or type they annotate; `*` matches any run
+    /// of characters
+    #[serde(default)]
+    pub attributes: Vec<String>,
+
+    /// Base types whose subclasses the generated code instantiates; such
+    /// classes are entry points
+    #[serde(default)]
+    pub bases: Vec<String>,
+
+    /// Names of the members the generator adds to the enclosing type.
+    /// `{name}` is the annotated member's name, `{Name}` the same without a
+    /// leading `_` / `m_` or trailing `Async` and capitalised.
+    #[serde(default)]
+    pub members: Vec<String>,
+}
+
+/// `MAGIC` analyzer settings
+///
+/// ```toml
+/// [[magic_numbers.patterns]]
+/// language  = "python"        # "python" or "typescript"; omit for both
+/// function  = "connect"       # callee name; omit to match any call
+/// parameter = "timeout_secs"  # keyword argument / object property name
+/// kind      = "timeout"       # timeout | retries | pool_size | ttl | backoff
+/// max       = 120             # Warning above this (seconds for durations)
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MagicNumbersConfig {
+    /// Checked before the built-in pattern table
+    #[serde(default)]
+    pub patterns: Vec<TunablePatternConfig>,
+}
+
+/// A numeric keyword argument / object property to treat as a tunable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TunablePatternConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+
+    pub parameter: String,
+
+    pub kind: String,
+
+    /// Unit of the literal for durations: "ms", "s", "min", "h" (default:
+    /// from the parameter name, else seconds in Python and ms in TypeScript)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+
+    /// Warning below this value (overrides the kind's default bound)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+
+    /// Warning above this value (overrides the kind's default bound)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+}
+
+/// `RES` analyzer settings
+///
+/// ```toml
+/// [resource_leaks]
+/// closeable_types = ["PooledConnection", "BlobHandle"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResourceLeaksConfig {
+    /// Type names treated as closeable in addition to the built-in list
+    #[serde(default)]
+    pub closeable_types: Vec<String>,
+}
+
+/// `LOG` analyzer settings
+///
+/// ```toml
+/// [sensitive_logging]
+/// sensitive_names = ["iban", "date_of_birth"]
+/// allow_names = ["card", "csrf_token"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SensitiveLoggingConfig {
+    /// Names flagged in log calls in addition to the built-in list
+    #[serde(default)]
+    pub sensitive_names: Vec<String>,
+    /// Built-in names to drop, and identifiers never flagged
+    #[serde(default)]
+    pub allow_names: Vec<String>,
+}
+
+/// `K8S` analyzer settings — every rule is on unless switched off
+///
+/// ```toml
+/// [kubernetes]
+/// probes = false
+/// helm_values = false
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KubernetesConfig {
+    /// Containers without `resources.limits`
+    #[serde(default = "default_true")]
+    pub resource_limits: bool,
+    /// `securityContext.privileged: true`
+    #[serde(default = "default_true")]
+    pub privileged: bool,
+    /// `hostNetwork`, `hostPID` or `hostIPC` set on a pod
+    #[serde(default = "default_true")]
+    pub host_namespaces: bool,
+    /// Images with neither a digest nor a version tag
+    #[serde(default = "default_true")]
+    pub image_pinning: bool,
+    /// Secret-looking env vars set with a literal `value`
+    #[serde(default = "default_true")]
+    pub env_secrets: bool,
+    /// Long-running workloads without liveness/readiness probes
+    #[serde(default = "default_true")]
+    pub probes: bool,
+    /// Check Helm chart `values.yaml` defaults
+    #[serde(default = "default_true")]
+    pub helm_values: bool,
+}
+
+impl Default for KubernetesConfig {
+    fn default() -> Self {
+        Self {
+            resource_limits: true,
+            privileged: true,
+            host_namespaces: true,
+            image_pinning: true,
+            env_secrets: true,
+            probes: true,
+            helm_values: true,
+        }
+    }
+}
+
+/// A custom analysis profile — a named bundle of existing settings
+///
+/// Unset keys inherit from `extends` (default: the built-in profile of the
+/// same name, else `standard`).
+///
+/// ```toml
+/// [profiles.nightly]
+/// extends = "deep"
+/// fail_on = "error"
+/// min_confidence = "medium"
+/// exclude = ["vendor/**"]
+///
+/// [profiles.nightly.modules]
+/// complexity = true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileConfig {
+    /// Profile to inherit unset keys from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    /// Which files to analyze: "changed", "diff" or "full"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<Scope>,
+    /// Build the code graph (impact analysis and graph analyzers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graph: Option<bool>,
+    /// Run LLM reasoning
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai: Option<bool>,
+    /// Severity threshold for non-zero exit code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fail_on: Option<String>,
+    /// Drop findings below this confidence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_confidence: Option<Confidence>,
+    /// Drop findings below this severity: "error", "warning" or "info"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_severity: Option<String>,
+    /// Cyclomatic complexity threshold, over `[modules] complexity_threshold`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complexity_threshold: Option<usize>,
+    /// Findings displayed per rule; the rest only count in the summary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_per_rule: Option<usize>,
+    /// Scan git history for secrets no longer in HEAD
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_secrets: Option<bool>,
+    /// Module switches applied on top of `[modules]`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub modules: BTreeMap<String, bool>,
+    /// Extra ignore globs, added to `[ignore] paths`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+/// Analyzers driven entirely by config entries
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnalyzersConfig {
+    /// Spec / generated-code pairs checked by the `GEN` analyzer
+    #[serde(default)]
+    pub generated: Vec<GeneratedCodeConfig>,
+
+    /// Settings of the `SKEW` analyzer
+    #[serde(default)]
+    pub skew: SkewConfig,
+
+    /// Settings of the `CMPLX` analyzer
+    #[serde(default)]
+    pub complexity: ComplexityConfig,
+
+    /// Settings of the `HOT` analyzer
+    #[serde(default)]
+    pub hotspots: HotspotsConfig,
+
+    /// Settings of the `OWN` analyzer
+    #[serde(default)]
+    pub ownership: OwnershipConfig,
+
+    /// Settings of the `I18N` analyzer
+    #[serde(default)]
+    pub i18n: I18nConfig,
+
+    /// Settings of the `FLAG` analyzer
+    #[serde(default)]
+    pub feature_flags: FeatureFlagsConfig,
+
+    /// Settings of the `DBG` analyzer
+    #[serde(default)]
+    pub debug_artifacts: DebugArtifactsConfig,
+
+    /// Settings of the `TEST` analyzer
+    #[serde(default)]
+    pub test_quality: TestQualityConfig,
+
+    /// Settings of the `COPY` analyzer
+    #[serde(default)]
+    pub copy: CopyConfig,
+
+    /// Settings of the `NUM` analyzer
+    #[serde(default)]
+    pub numeric: NumericConfig,
+
+    /// Settings of the `PAGE` analyzer
+    #[serde(default)]
+    pub pagination: PaginationConfig,
+
+    /// Settings of the `WEBSEC` analyzer
+    #[serde(default)]
+    pub websec: WebsecConfig,
+
+    /// Settings of the `CONTRACT` analyzer
+    #[serde(default)]
+    pub contract: ContractConfig,
+
+    /// Settings of the `REPO` analyzer
+    #[serde(default)]
+    pub repository: RepositoryConfig,
+
+    /// Settings of the `COUPLE` analyzer
+    #[serde(default)]
+    pub coupling: CouplingConfig,
+
+    /// Settings of the `DOC` analyzer
+    #[serde(default)]
+    pub docs: DocsConfig,
+
+    /// Settings of the `RACE` analyzer
+    #[serde(default)]
+    pub concurrency: ConcurrencyConfig,
+
+    /// Custom detectors for the `SEC` analyzer
+    #[serde(default)]
+    pub secrets: SecretsConfig,
+
+    /// Sub-rule selection per module, keyed by module name
+    ///
+    /// ```toml
+    /// [analyzers.error_handling]
+    /// only = ["swallowed"]
+    ///
+    /// [analyzers.async_patterns]
+    /// disable = ["return_await"]
+    /// ```
+    #[serde(flatten)]
+    pub rules: BTreeMap<String, RuleSelection>,
+}
+
+/// Which sub-rules of a module report findings, and in which repositories
+///
+/// An empty `only` selects every sub-rule; `disable` is applied after it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleSelection {
+    /// Report only these sub-rules
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub only: Vec<String>,
+
+    /// Never report these sub-rules
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disable: Vec<String>,
+
+    /// Report findings only in this percentage of repositories, picked by
+    /// a stable hash; the others run the module in shadow mode (see
+    /// [`crate::rollout`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollout_percent: Option<u8>,
+}
+
+/// Selection for modules without an `[analyzers.<module>]` table
+static ALL_RULES: RuleSelection = RuleSelection {
+    only: Vec::new(),
+    disable: Vec::new(),
+    rollout_percent: None,
+};
+
+impl RuleSelection {
+    /// Whether findings of sub-rule `rule` are reported
+    pub fn allows(&self, rule: &str) -> bool {
+        (self.only.is_empty() || self.only.iter().any(|r| r == rule))
+            && !self.disable.iter().any(|r| r == rule)
+    }
+
+    /// Every sub-rule name this selection mentions
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.only.iter().chain(&self.disable).map(String::as_str)
+    }
+}
+
+/// `SKEW` analyzer settings
+///
+/// ```toml
+/// [analyzers.skew]
+/// max_versions = 2
+/// keys  = ["tsconfig.json:compilerOptions.strict", "Cargo.toml:package.edition"]
+/// allow = ["react@17:packages/legacy-admin"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkewConfig {
+    /// Flag a dependency declared at more than this many distinct versions
+    #[serde(default = "default_skew_max_versions")]
+    pub max_versions: usize,
+
+    /// Config values compared across same-named files, as `file:dotted.key`
+    #[serde(default = "default_skew_keys")]
+    pub keys: Vec<String>,
+
+    /// Intentional exceptions, as `name@version:path` (`path` optional)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+fn default_skew_max_versions() -> usize {
+    2
+}
+
+fn default_skew_keys() -> Vec<String> {
+    [
+        "tsconfig.json:compilerOptions.strict",
+        "pyproject.toml:project.requires-python",
+        "Cargo.toml:package.edition",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+impl Default for SkewConfig {
+    fn default() -> Self {
+        Self {
+            max_versions: default_skew_max_versions(),
+            keys: default_skew_keys(),
+            allow: Vec::new(),
+            selection: RuleSelection::default(),
+        }
+    }
+}
+
+/// `I18N` analyzer settings
+///
+/// ```toml
+/// [analyzers.i18n]
+/// catalogs       = ["web/locales/{locale}/{namespace}.json"]
+/// default_locale = "en"
+/// frameworks     = ["i18next"]
+/// functions      = ["translate"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct I18nConfig {
+    /// Catalog globs; `{locale}` and the optional `{namespace}` placeholder
+    /// each stand for one path segment (or part of one)
+    #[serde(default = "default_i18n_catalogs")]
+    pub catalogs: Vec<String>,
+
+    /// Locale the others are compared with for missing namespaces
+    #[serde(default = "default_i18n_default_locale")]
+    pub default_locale: String,
+
+    /// Lookup tables to recognise: "i18next", "vue-i18n", "angular", "gettext"
+    #[serde(default = "default_i18n_frameworks")]
+    pub frameworks: Vec<String>,
+
+    /// Extra callees whose first argument is a translation key
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<String>,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+fn default_i18n_catalogs() -> Vec<String> {
+    [
+        "**/locales/{locale}/{namespace}.json",
+        "**/locales/{locale}.json",
+        "**/locales/{locale}/{namespace}.yml",
+        "**/locales/{locale}/{namespace}.yaml",
+        "**/locales/{locale}.yml",
+        "**/locales/{locale}.yaml",
+        "**/locale/{locale}/LC_MESSAGES/{namespace}.po",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_i18n_default_locale() -> String {
+    "en".to_string()
+}
+
+fn default_i18n_frameworks() -> Vec<String> {
+    crate::analyzer::translations::lookups::FRAMEWORKS
+        .iter()
+        .map(|f| f.name.to_string())
+        .collect()
+}
+
+impl Default for I18nConfig {
+    fn default() -> Self {
+        Self {
+            catalogs: default_i18n_catalogs(),
+            default_locale: default_i18n_default_locale(),
+            frameworks: default_i18n_frameworks(),
+            functions: Vec::new(),
+            selection: RuleSelection::default(),
+        }
+    }
+}
+
+/// `FLAG` analyzer settings
+///
+/// ```toml
+/// [analyzers.feature_flags]
+/// inventory = ".revet/flags.toml"
+/// sdks      = ["launchdarkly"]
+/// functions = ["isFeatureOn", "flags.enabled"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureFlagsConfig {
+    /// Flag inventory, relative to the repository root
+    #[serde(default = "default_flags_inventory")]
+    pub inventory: String,
+
+    /// Flag-check tables to recognise: "launchdarkly", "unleash", "openfeature"
+    #[serde(default = "default_flags_sdks")]
+    pub sdks: Vec<String>,
+
+    /// Extra callees whose first argument is a flag name; a leading dot
+    /// (`.enabled`) matches any receiver
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<String>,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+fn default_flags_inventory() -> String {
+    ".revet/flags.toml".to_string()
+}
+
+fn default_flags_sdks() -> Vec<String> {
+    crate::analyzer::feature_flags::checks::SDKS
+        .iter()
+        .map(|s| s.name.to_string())
+        .collect()
+}
+
+impl Default for FeatureFlagsConfig {
+    fn default() -> Self {
+        Self {
+            inventory: default_flags_inventory(),
+            sdks: default_flags_sdks(),
+            functions: Vec::new(),
+            selection: RuleSelection::default(),
+        }
+    }
+}
+
+/// `DBG` analyzer settings
+///
+/// ```toml
+/// [analyzers.debug_artifacts]
+/// print_patterns = ["here", "got here", "asdf\\w*"]
+/// comment_lines  = 5
+///
+/// [analyzers.debug_artifacts.severity]
+/// debugger       = "error"
+/// debug_print    = "warning"
+/// commented_code = "info"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebugArtifactsConfig {
+    /// Regexes a print's whole message must match to count as leftover
+    /// debugging (case-insensitive; a trailing number is allowed)
+    #[serde(default = "default_debug_print_patterns")]
+    pub print_patterns: Vec<String>,
+
+    /// Consecutive comment lines of code reported as a commented-out block
+    #[serde(default = "default_debug_comment_lines")]
+    pub comment_lines: usize,
+
+    /// Severity per sub-rule: "error", "warning" or "info"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, String>,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+fn default_debug_print_patterns() -> Vec<String> {
+    crate::analyzer::debug_artifacts::calls::PRINT_PATTERNS
+        .iter()
+        .map(|p| p.to_string())
+        .collect()
+}
+
+fn default_debug_comment_lines() -> usize {
+    5
+}
+
+impl Default for DebugArtifactsConfig {
+    fn default() -> Self {
+        Self {
+            print_patterns: default_debug_print_patterns(),
+            comment_lines: default_debug_comment_lines(),
+            severity: BTreeMap::new(),
+            selection: RuleSelection::default(),
+        }
+    }
+}
+
+/// `TEST` analyzer settings
+///
+/// ```toml
+/// [analyzers.test_quality]
+/// skip_max_age_days  = 90
+/// assertion_patterns = ["^ensure_\\w+$", "^validate\\w*$"]
+///
+/// [analyzers.test_quality.severity]
+/// sleep           = "warning"
+/// no_assertions   = "warning"
+/// stale_skip      = "info"
+/// broad_exception = "warning"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestQualityConfig {
+    /// Days a test may stay skipped or disabled before `stale_skip` reports it
+    #[serde(default = "default_test_skip_max_age_days")]
+    pub skip_max_age_days: u64,
+
+    /// Regexes for the names of project assertion helpers; a test calling one
+    /// has an assertion
+    #[serde(default)]
+    pub assertion_patterns: Vec<String>,
+
+    /// Severity per sub-rule: "error", "warning" or "info"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, String>,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+fn default_test_skip_max_age_days() -> u64 {
+    90
+}
+
+impl Default for TestQualityConfig {
+    fn default() -> Self {
+        Self {
+            skip_max_age_days: default_test_skip_max_age_days(),
+            assertion_patterns: Vec::new(),
+            severity: BTreeMap::new(),
+            selection: RuleSelection::default(),
+        }
+    }
+}
+
+/// `COPY` analyzer settings
+///
+/// ```toml
+/// [analyzers.copy]
+/// version_lag = 1
+/// allow_paths = ["**/CHANGELOG*", "docs/history/**"]
+/// banned      = [{ term = "OldBrand", replacement = "NewBrand", severity = "warning" }]
+///
+/// [analyzers.copy.severity]
+/// copyright_year    = "info"
+/// version_reference = "warning"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CopyConfig {
+    /// Major versions a `version X.Y` reference may trail the version of
+    /// the nearest manifest before it is reported
+    #[serde(default = "default_copy_version_lag")]
+    pub version_lag: u64,
+
+    /// Globs of files that record history (changelogs, release notes) and
+    /// are not checked
+    #[serde(default = "default_copy_allow_paths")]
+    pub allow_paths: Vec<String>,
+
+    /// Retired names to report in user-facing copy
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub banned: Vec<BannedTerm>,
+
+    /// Severity per sub-rule: "error", "warning" or "info"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, String>,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+/// A `[[analyzers.copy.banned]]` term
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BannedTerm {
+    /// Matched as a whole word, case-sensitively
+    pub term: String,
+
+    /// What to say instead; `revet --fix` substitutes it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+
+    /// "error", "warning" or "info" (default: the `banned_term` severity)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+}
+
+fn default_copy_version_lag() -> u64 {
+    1
+}
+
+fn default_copy_allow_paths() -> Vec<String> {
+    [
+        "**/CHANGELOG*",
+        "**/CHANGES*",
+        "**/HISTORY*",
+        "**/RELEASE_NOTES*",
+        "**/RELEASES*",
+        "**/NEWS*",
+    ]
+    .iter()
+    .map(|p| p.to_string())
+    .collect()
+}
+
+impl Default for CopyConfig {
+    fn default() -> Self {
+        Self {
+            version_lag: default_copy_version_lag(),
+            allow_paths: default_copy_allow_paths(),
+            banned: Vec::new(),
+            severity: BTreeMap::new(),
+            selection: RuleSelection::default(),
+        }
+    }
+}
+
+/// `NUM` analyzer settings
+///
+/// ```toml
+/// [analyzers.numeric]
+/// money_names   = ["price", "amount", "total", "balance", "fee", "payout"]
+/// id_names      = ["id", "snowflake"]
+/// include_tests = false
+///
+/// [analyzers.numeric.severity]
+/// money_float       = "error"
+/// float_equality    = "info"
+/// unsafe_integer_id = "warning"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NumericConfig {
+    /// Words that mark a name as an amount of money; `money_float` reports
+    /// floats whose name ends in one (`unit_price`, `totalAmount`, `fees`)
+    #[serde(default = "default_numeric_money_names")]
+    pub money_names: Vec<String>,
+
+    /// Words that mark a name as an identifier, for `unsafe_integer_id`
+    #[serde(default = "default_numeric_id_names")]
+    pub id_names: Vec<String>,
+
+    /// Also check test code
+    #[serde(default)]
+    pub include_tests: bool,
+
+    /// Severity per sub-rule: "error", "warning" or "info"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, String>,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+fn default_numeric_money_names() -> Vec<String> {
+    [
+        "price", "amount", "total", "subtotal", "balance", "fee", "cost", "tax",
+    ]
+    .iter()
+    .map(|n| n.to_string())
+    .collect()
+}
+
+fn default_numeric_id_names() -> Vec<String> {
+    vec!["id".to_string()]
+}
+
+impl Default for NumericConfig {
+    fn default() -> Self {
+        Self {
+            money_names: default_numeric_money_names(),
+            id_names: default_numeric_id_names(),
+            include_tests: false,
+            severity: BTreeMap::new(),
+            selection: RuleSelection::default(),
+        }
+    }
+}
+
+/// `PAGE` analyzer settings
+///
+/// ```toml
+/// [analyzers.pagination]
+/// small_tables = ["countries", "Currency"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaginationConfig {
+    /// Models and tables small enough to return whole, matched
+    /// case-insensitively against the model or table a handler reads
+    #[serde(default)]
+    pub small_tables: Vec<String>,
+}
+
+/// `WEBSEC` analyzer settings
+///
+/// ```toml
+/// [analyzers.websec]
+/// disable = ["security_headers_missing"]
+///
+/// [analyzers.websec.severity]
+/// cookie_samesite = "info"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebsecConfig {
+    /// Severity per sub-rule: "error", "warning" or "info"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, String>,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+/// `CONTRACT` analyzer settings
+///
+/// ```toml
+/// [analyzers.contract]
+/// base_paths = ["/api"]   # prefixes the frontend puts before backend routes
+/// disable = ["uncalled_endpoint"]
+///
+/// [analyzers.contract.severity]
+/// field_mismatch = "error"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractConfig {
+    /// Path prefixes stripped from client URLs when matching them to
+    /// endpoints (a proxy or gateway mount point)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub base_paths: Vec<String>,
+
+    /// Severity per sub-rule: "error", "warning" or "info"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, String>,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+/// `REPO` analyzer settings
+///
+/// ```toml
+/// [analyzers.repository]
+/// stale_baseline_entries = 400      # baselines this large...
+/// stale_baseline_days    = 90       # ...and created this long ago are stale
+/// protected_branch       = "main"   # expects a CODEOWNERS file
+/// required_checks        = ["test", "lint"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepositoryConfig {
+    /// Entries from which a baseline counts as large
+    #[serde(default = "default_stale_baseline_entries")]
+    pub stale_baseline_entries: usize,
+
+    /// Age in days from which a large baseline is reported as stale
+    #[serde(default = "default_stale_baseline_days")]
+    pub stale_baseline_days: u64,
+
+    /// Branch the repository declares protected; its reviews need owners,
+    /// so a CODEOWNERS file is expected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protected_branch: Option<String>,
+
+    /// Status checks the protected branch requires, each expected as the id
+    /// or `name` of a job in `.github/workflows`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_checks: Vec<String>,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+fn default_stale_baseline_entries() -> usize {
+    400
+}
+
+fn default_stale_baseline_days() -> u64 {
+    90
+}
+
+impl Default for RepositoryConfig {
+    fn default() -> Self {
+        Self {
+            stale_baseline_entries: default_stale_baseline_entries(),
+            stale_baseline_days: default_stale_baseline_days(),
+            protected_branch: None,
+            required_checks: Vec::new(),
+            selection: RuleSelection::default(),
+        }
+    }
+}
+
+/// `COUPLE` analyzer settings
+///
+/// ```toml
+/// [analyzers.coupling]
+/// envy_ratio         = 3.0   # references into another file vs. its own
+/// envy_min_refs      = 6
+/// intimacy_density   = 0.5   # edges both ways per symbol of the two files
+/// intimacy_min_edges = 8
+/// max_pairs          = 10    # densest pairs reported
+/// related            = 5     # symbols listed per finding
+///
+/// [analyzers.coupling.severity]
+/// envy     = "info"
+/// intimacy = "warning"
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CouplingConfig {
+    /// A function is envious when its references into one other file reach
+    /// this multiple of those into its own
+    #[serde(default = "default_coupling_envy_ratio")]
+    pub envy_ratio: f64,
+
+    /// Never flag envy of fewer references into the other file than this
+    #[serde(default = "default_coupling_envy_min_refs")]
+    pub envy_min_refs: usize,
+
+    /// Two files are intimate from this many edges between them (both
+    /// directions) per symbol of the two
+    #[serde(default = "default_coupling_intimacy_density")]
+    pub intimacy_density: f64,
+
+    /// Never flag a pair with fewer edges between them than this
+    #[serde(default = "default_coupling_intimacy_min_edges")]
+    pub intimacy_min_edges: usize,
+
+    /// Most intimate pairs reported per run, densest first
+    #[serde(default = "default_coupling_max_pairs")]
+    pub max_pairs: usize,
+
+    /// Symbols listed as locations of each finding
+    #[serde(default = "default_coupling_related")]
+    pub related: usize,
+
+    /// Severity per sub-rule: "error", "warning" or "info"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, String>,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+fn default_coupling_envy_ratio() -> f64 {
+    3.0
+}
+
+fn default_coupling_envy_min_refs() -> usize {
+    6
+}
+
+fn default_coupling_intimacy_density() -> f64 {
+    0.5
+}
+
+fn default_coupling_intimacy_min_edges() -> usize {
+    8
+}
+
+fn default_coupling_max_pairs() -> usize {
+    10
+}
+
+fn default_coupling_related() -> usize {
+    5
+}
+
+impl Default for CouplingConfig {
+    fn default() -> Self {
+        Self {
+            envy_ratio: default_coupling_envy_ratio(),
+            envy_min_refs: default_coupling_envy_min_refs(),
+            intimacy_density: default_coupling_intimacy_density(),
+            intimacy_min_edges: default_coupling_intimacy_min_edges(),
+            max_pairs: default_coupling_max_pairs(),
+            related: default_coupling_related(),
+            severity: BTreeMap::new(),
+            selection: RuleSelection::default(),
+        }
+    }
+}
+
+/// `DOC` analyzer settings
+///
+/// ```toml
+/// [analyzers.docs]
+/// min_coverage   = 0.7    # per project; unset = no gate
+/// skip_getters   = true   # get…/is…/has…, @property, C# properties
+/// skip_constants = true   # declared constant or ALL_CAPS
+/// skip_reexports = true   # type aliases naming another type
+///
+/// [analyzers.docs.severity]
+/// undocumented = "info"
+/// coverage     = "error"
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocsConfig {
+    /// Documented share of each project's public symbols (0 to 1) below
+    /// which it gets a `coverage` finding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_coverage: Option<f64>,
+
+    /// Leave out getters: `get…`, `is…` and `has…` functions without
+    /// parameters, Python properties, C# properties and one-line accessors
+    #[serde(default = "default_true")]
+    pub skip_getters: bool,
+
+    /// Leave out constants: declared constant or named in `ALL_CAPS`
+    #[serde(default = "default_true")]
+    pub skip_constants: bool,
+
+    /// Leave out type aliases that only name another type
+    #[serde(default = "default_true")]
+    pub skip_reexports: bool,
+
+    /// Severity per sub-rule: "error", "warning" or "info"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, String>,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+impl Default for DocsConfig {
+    fn default() -> Self {
+        Self {
+            min_coverage: None,
+            skip_getters: true,
+            skip_constants: true,
+            skip_reexports: true,
+            severity: BTreeMap::new(),
+            selection: RuleSelection::default(),
+        }
+    }
+}
+
+/// `RACE` analyzer settings
+///
+/// ```toml
+/// [analyzers.concurrency]
+/// entry_points = ["gevent.spawn"]  # calls starting concurrent work, on top of the built-in ones
+/// handlers     = true              # request handlers run concurrently
+/// max_depth    = 8                 # calls followed from an entry point
+///
+/// [analyzers.concurrency.severity]
+/// shared_state = "error"
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConcurrencyConfig {
+    /// Calls that run their function argument concurrently, besides
+    /// `Thread(target=...)`, executor submissions and the like: a dotted
+    /// name matches itself and any call ending in it (`spawn` matches
+    /// `gevent.spawn`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entry_points: Vec<String>,
+
+    /// Take route handlers (FastAPI, Flask, Express, Next.js) as entry
+    /// points
+    #[serde(default = "default_true")]
+    pub handlers: bool,
+
+    /// Calls followed from an entry point before giving up
+    #[serde(default = "default_concurrency_max_depth")]
+    pub max_depth: usize,
+
+    /// Severity per sub-rule: "error", "warning" or "info"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, String>,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+fn default_concurrency_max_depth() -> usize {
+    8
+}
+
+impl Default for ConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            entry_points: Vec::new(),
+            handlers: true,
+            max_depth: default_concurrency_max_depth(),
+            severity: BTreeMap::new(),
+            selection: RuleSelection::default(),
+        }
+    }
+}
+
+/// `HOT` analyzer settings
+///
+/// ```toml
+/// [analyzers.hotspots]
+/// window      = "90d"   # history counted: <n>d, <n>w, <n>m or <n>y
+/// percentile  = 90      # flag files in the top 10% of churn and complexity
+/// min_changes = 2
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotspotsConfig {
+    /// Length of the history window, ending at the HEAD commit
+    #[serde(default = "default_hotspots_window")]
+    pub window: String,
+
+    /// Flag files at or above this percentile of both change count and
+    /// aggregate complexity (1–99)
+    #[serde(default = "default_hotspots_percentile")]
+    pub percentile: u8,
+
+    /// Never flag files changed fewer times than this
+    #[serde(default = "default_hotspots_min_changes")]
+    pub min_changes: usize,
+}
+
+fn default_hotspots_window() -> String {
+    "90d".to_string()
+}
+
+fn default_hotspots_percentile() -> u8 {
+    90
+}
+
+fn default_hotspots_min_changes() -> usize {
+    2
+}
+
+impl Default for HotspotsConfig {
+    fn default() -> Self {
+        Self {
+            window: default_hotspots_window(),
+            percentile: default_hotspots_percentile(),
+            min_changes: default_hotspots_min_changes(),
+        }
+    }
+}
+
+/// `OWN` analyzer settings
+///
+/// ```toml
+/// [analyzers.ownership]
+/// window       = "180d"   # authors of commits in this window are active
+/// active       = ["alice@", "bob@example.com"]  # or an explicit roster
+/// roster_file  = "MAINTAINERS"                  # one entry per line
+/// min_share    = 10       # an active author needs 10% of a file's lines
+/// min_lines    = 10
+/// max_files    = 2000     # files blamed per run, in path order
+/// complexity   = 30       # escalate to warning from this file complexity
+/// rollup       = "file"   # or "directory"
+/// hash_authors = false
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnershipConfig {
+    /// History window, ending at the HEAD commit, whose commit authors
+    /// count as active when no roster is given
+    #[serde(default = "default_ownership_window")]
+    pub window: String,
+
+    /// Active maintainers: an email, an email prefix ending in `@`, or the
+    /// part before the `@`. Replaces the window when set
+    #[serde(default)]
+    pub active: Vec<String>,
+
+    /// File (relative to the repository root) listing more active
+    /// maintainers, one per line; `#` starts a comment
+    #[serde(default)]
+    pub roster_file: Option<String>,
+
+    /// Share of lines (percent) an active author needs for the file to
+    /// count as maintained
+    #[serde(default = "default_ownership_min_share")]
+    pub min_share: u8,
+
+    /// Files (or directories) with fewer lines are never reported
+    #[serde(default = "default_ownership_min_lines")]
+    pub min_lines: usize,
+
+    /// Most files blamed in one run, in path order
+    #[serde(default = "default_ownership_max_files")]
+    pub max_files: usize,
+
+    /// Aggregate complexity from which an orphaned file is a warning
+    #[serde(default = "default_ownership_complexity")]
+    pub complexity: usize,
+
+    /// `file` reports files; `directory` rolls files up into their directory
+    #[serde(default = "default_ownership_rollup")]
+    pub rollup: String,
+
+    /// Show authors as `author-<hash>` instead of their email
+    #[serde(default)]
+    pub hash_authors: bool,
+}
+
+fn default_ownership_window() -> String {
+    "180d".to_string()
+}
+
+fn default_ownership_min_share() -> u8 {
+    10
+}
+
+fn default_ownership_min_lines() -> usize {
+    10
+}
+
+fn default_ownership_max_files() -> usize {
+    2000
+}
+
+fn default_ownership_complexity() -> usize {
+    30
+}
+
+fn default_ownership_rollup() -> String {
+    "file".to_string()
+}
+
+impl Default for OwnershipConfig {
+    fn default() -> Self {
+        Self {
+            window: default_ownership_window(),
+            active: Vec::new(),
+            roster_file: None,
+            min_share: default_ownership_min_share(),
+            min_lines: default_ownership_min_lines(),
+            max_files: default_ownership_max_files(),
+            complexity: default_ownership_complexity(),
+            rollup: default_ownership_rollup(),
+            hash_authors: false,
+        }
+    }
+}
+
+/// `CMPLX` analyzer settings
+///
+/// ```toml
+/// [analyzers.complexity]
+/// mode = "delta"   # diff-based runs only report functions a change made worse
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplexityConfig {
+    /// "absolute" (every function over a threshold) or "delta"
+    #[serde(default = "default_complexity_mode")]
+    pub mode: String,
+
+    /// Sub-rule selection, as for other modules
+    #[serde(flatten)]
+    pub selection: RuleSelection,
+}
+
+fn default_complexity_mode() -> String {
+    "absolute".to_string()
+}
+
+impl Default for ComplexityConfig {
+    fn default() -> Self {
+        Self {
+            mode: default_complexity_mode(),
+            selection: RuleSelection::default(),
+        }
+    }
+}
+
+/// `SEC` analyzer settings: detectors for in-house token formats, run
+/// alongside the built-in patterns
+///
+/// ```toml
+/// [analyzers.secrets]
+/// min_entropy = 3.5   # bits per character for `entropy_check`; default 3.0
+///
+/// [[analyzers.secrets.patterns]]
+/// name                = "acme-live"
+/// regex               = "acme_live_(?P<secret>[a-z0-9]{32})"
+/// severity            = "error"
+/// entropy_check       = true
+/// must_match_test     = "key = acme_live_8f3k2m9q1z7x4c6v0b5n8m2l1k9j3h7g"
+/// must_not_match_test = "key = acme_live_00000000000000000000000000000000"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretsConfig {
+    #[serde(default)]
+    pub patterns: Vec<SecretPatternConfig>,
+
+    /// Minimum Shannon entropy, in bits per character, of a secret value
+    /// that passes `entropy_check`
+    #[serde(default = "default_min_entropy")]
+    pub min_entropy: f64,
+}
+
+fn default_min_entropy() -> f64 {
+    crate::analyzer::secret_exposure::MIN_ENTROPY
+}
+
+impl Default for SecretsConfig {
+    fn default() -> Self {
+        Self {
+            patterns: Vec::new(),
+            min_entropy: default_min_entropy(),
+        }
+    }
+}
+
+/// One custom secret detector, reported as sub-rule `custom-<name>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretPatternConfig {
+    /// Shown in findings; also names the sub-rule
+    pub name: String,
+
+    /// Regex (Rust `regex` crate syntax). The `secret` capture group, else
+    /// the first group, else the whole match is the secret value.
+    pub regex: toml::Spanned<String>,
+
+    /// "error", "warning" or "info"
+    pub severity: String,
+
+    /// Skip matches whose secret value looks like a placeholder (low
+    /// Shannon entropy)
+    #[serde(default)]
+    pub entropy_check: bool,
+
+    /// A line the detector must report; checked when the config is loaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub must_match_test: Option<String>,
+
+    /// A line the detector must not report; checked when the config is loaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub must_not_match_test: Option<String>,
+}
+
+impl SecretsConfig {
+    /// Errors for entries that don't compile or fail their own test strings.
+    ///
+    /// With the config's TOML `source`, each error names the line of the
+    /// entry's `regex`.
+    pub fn check(&self, source: Option<&str>) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        for (i, entry) in self.patterns.iter().enumerate() {
+            let mut label = format!(
+                "[[analyzers.secrets.patterns]] entry {} ({:?})",
+                i, entry.name
+            );
+            if let Some(source) = source {
+                let start = entry.regex.span().start.min(source.len());
+                let line = source[..start].matches('\n').count() + 1;
+                label = format!("{}, line {}", label, line);
+            }
+            if let Err(e) =
+                crate::analyzer::secret_exposure::CustomPattern::compile(entry, self.min_entropy)
+            {
+                errors.push(format!("{}: {}", label, e));
+            } else if !seen.insert(crate::analyzer::secret_exposure::custom_rule(&entry.name)) {
+                errors.push(format!(
+                    "{}: name is already used by another pattern",
+                    label
+                ));
+            }
+        }
+        errors
+    }
+}
+
+/// A spec and the code generated from it
+///
+/// ```toml
+/// [[analyzers.generated]]
+/// spec             = "api/openapi.yaml"
+/// output_dir       = "src/client/"
+/// command          = "npm run generate-client"
+/// hash_file        = ".revet/gen-hashes.toml"
+/// allow_regenerate = true
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedCodeConfig {
+    /// Spec file, relative to the repo root
+    pub spec: String,
+
+    /// Directory holding the generated code, relative to the repo root
+    pub output_dir: String,
+
+    /// Command that regenerates `output_dir`, run from the repo root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    /// Where recorded hashes are kept (commit this file)
+    #[serde(default = "default_gen_hash_file")]
+    pub hash_file: String,
+
+    /// Let `--fix` run `command` and verify the result
+    #[serde(default)]
+    pub allow_regenerate: bool,
+}
+
+fn default_gen_hash_file() -> String {
+    ".revet/gen-hashes.toml".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneralConfig {
+    /// Languages to analyze (auto-detected if empty)
+    #[serde(default)]
+    pub languages: Vec<String>,
+
+    /// Default diff base
+    #[serde(default = "default_diff_base")]
+    pub diff_base: String,
+
+    /// Severity threshold for non-zero exit code
+    #[serde(default = "default_fail_on")]
+    pub fail_on: String,
+
+    /// Analysis profile used when `--profile` is not given (default: "standard")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModulesConfig {
+    /// Enable/disable domain modules
+    #[serde(default = "default_true")]
+    pub ml: bool,
+
+    #[serde(default = "default_true")]
+    pub security: bool,
+
+    #[serde(default)]
+    pub infra: bool,
+
+    #[serde(default)]
+    pub react: bool,
+
+    #[serde(default)]
+    pub async_patterns: bool,
+
+    #[serde(default)]
+    pub dependency: bool,
+
+    /// Offline advisory file (JSON) matched against lockfile components by
+    /// `revet sbom`. Relative paths resolve against the repository root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependency_advisories: Option<String>,
+
+    #[serde(default)]
+    pub error_handling: bool,
+
+    /// Detect unused exported symbols (opt-in, can be noisy)
+    #[serde(default)]
+    pub dead_code: bool,
+
+    /// Detect circular import chains (default on)
+    #[serde(default = "default_true")]
+    pub cycles: bool,
+
+    /// Detect overly complex functions: length, parameter count, cyclomatic complexity, nesting
+    #[serde(default)]
+    pub complexity: bool,
+
+    /// Cyclomatic complexity threshold — warn above this value (default: 10, error at 2x)
+    #[serde(default = "default_complexity_threshold")]
+    pub complexity_threshold: usize,
+
+    /// Detect imported symbols that are never used within the same file
+    #[serde(default)]
+    pub dead_imports: bool,
+
+    /// Detect tools invoked in CI/scripts that are not declared in any manifest
+    #[serde(default)]
+    pub toolchain: bool,
+
+    /// Detect hardcoded IP addresses and production/staging URLs in source code
+    #[serde(default)]
+    pub hardcoded_endpoints: bool,
+
+    /// Detect unnamed numeric literals (magic numbers) that should be named constants
+    #[serde(default)]
+    pub magic_numbers: bool,
+
+    /// Detect unclosed JVM resources and multi-write methods without a transaction
+    #[serde(default)]
+    pub resource_leaks: bool,
+
+    /// Check Kubernetes manifests and Helm charts (`K8S`)
+    #[serde(default)]
+    pub kubernetes: bool,
+
+    /// Detect dependency versions and config values that drift across the
+    /// packages of a monorepo (`SKEW`)
+    #[serde(default)]
+    pub skew: bool,
+
+    /// Flag files that change often and are complex, from git history and
+    /// aggregate complexity (`HOT`)
+    #[serde(default)]
+    pub hotspots: bool,
+
+    /// Flag files whose lines were mostly written by people who no longer
+    /// commit, from git blame (`OWN`)
+    #[serde(default)]
+    pub ownership: bool,
+
+    /// In diff mode, check that every call site of a function the diff
+    /// changed the arity of or renamed was updated too (`REF`)
+    #[serde(default)]
+    pub refactor: bool,
+
+    /// Check translation catalogs against the keys code looks up (`I18N`)
+    #[serde(default)]
+    pub i18n: bool,
+
+    /// Check feature-flag checks against the flag inventory (`FLAG`)
+    #[serde(default)]
+    pub feature_flags: bool,
+
+    /// Detect committed debugger statements, print debugging and
+    /// commented-out code (`DBG`)
+    #[serde(default)]
+    pub debug_artifacts: bool,
+
+    /// Detect sleeps, assertion-free tests, long-skipped tests and broad
+    /// exception assertions in test code (`TEST`)
+    #[serde(default)]
+    pub test_quality: bool,
+
+    /// Detect stale copyright years, references to long-past versions and
+    /// retired product names in user-facing strings and docs (`COPY`)
+    #[serde(default)]
+    pub copy: bool,
+
+    /// Detect money held in floats, exact float comparisons and integer IDs
+    /// too long for a JavaScript number (`NUM`)
+    #[serde(default)]
+    pub numeric: bool,
+
+    /// Detect HTTP handlers returning whole query results without
+    /// pagination (`PAGE`)
+    #[serde(default)]
+    pub pagination: bool,
+
+    /// Detect images without alt text, unlabelled form fields, click
+    /// handlers without keyboard access and other a11y mistakes in JSX and
+    /// HTML templates (`A11Y`)
+    #[serde(default)]
+    pub a11y: bool,
+
+    /// Detect CORS allowing any origin with credentials, cookies without
+    /// HttpOnly/Secure/SameSite and security headers turned off in web
+    /// framework code (`WEBSEC`)
+    #[serde(default)]
+    pub websec: bool,
+
+    /// Match frontend `fetch`/`axios` and generated-client calls to the
+    /// backend endpoints of the same repository, and request bodies to the
+    /// models the handlers take (`CONTRACT`)
+    #[serde(default)]
+    pub contract: bool,
+
+    /// Check properties of the repository as a whole: a LICENSE file, a
+    /// stale baseline, security analyzers turned off and declared branch
+    /// protection expectations (`REPO`)
+    #[serde(default)]
+    pub repository: bool,
+
+    /// Flag APIs removed, deprecated or changed by the runtime versions
+    /// `[upgrade]` targets (`UPGRADE`); inert until a target is declared
+    /// or inferred from `pyproject.toml` / `package.json`
+    #[serde(default = "default_true")]
+    pub upgrade: bool,
+
+    /// Detect public functions/classes with no mention in any test file
+    #[serde(default)]
+    pub test_coverage: bool,
+
+    /// Detect feature envy and inappropriate intimacy between files
+    /// (`COUPLE`)
+    #[serde(default)]
+    pub coupling: bool,
+
+    /// Detect public symbols without documentation comments (`DOC`)
+    #[serde(default)]
+    pub docs: bool,
+
+    /// Detect shared mutable state reached from threads and request
+    /// handlers without a lock (`RACE`)
+    #[serde(default)]
+    pub concurrency: bool,
+
+    /// Detect copy-paste duplicate code blocks across files
+    #[serde(default)]
+    pub duplication: bool,
+
+    /// Audit `revet-ignore` comments: unknown rules, malformed directives
+    /// and suppressions that match nothing (`SUPP`)
+    #[serde(default = "default_true")]
+    pub suppressions: bool,
+
+    /// In diff runs, report how the change edits revet's own configuration:
+    /// analyzers disabled, thresholds raised, paths excluded, ... (`CONF`)
+    #[serde(default = "default_true")]
+    pub config_drift: bool,
+
+    /// Minimum block size (lines) to consider as a duplicate (default: 6)
+    #[serde(default = "default_duplication_min_lines")]
+    pub duplication_min_lines: usize,
+
+    /// Maximum transitive call-graph depth for impact analysis (default: 3)
+    #[serde(default = "default_call_graph_depth")]
+    pub call_graph_depth: usize,
+
+    /// Module-specific configurations
+    #[serde(flatten)]
+    pub module_configs: HashMap<String, toml::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AIConfig {
+    /// LLM provider: "anthropic" | "openai" | "ollama"
+    #[serde(default = "default_provider")]
+    pub provider: String,
+
+    /// Model name
+    #[serde(default = "default_model")]
+    pub model: String,
+
+    /// API key — can also be set via ANTHROPIC_API_KEY / OPENAI_API_KEY env var.
+    /// Not required for Ollama.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+
+    /// Max cost per run in USD
+    #[serde(default = "default_max_cost")]
+    pub max_cost_per_run: f64,
+
+    /// Base URL for the LLM API. Defaults to the provider's standard endpoint.
+    /// Set this to point Ollama at a non-default host/port, e.g. "http://10.0.0.5:11434".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+
+    /// Most tokens of source sent with each finding. The enclosing function
+    /// is sent whole when it fits; otherwise an excerpt of it is.
+    #[serde(default = "default_context_tokens")]
+    pub context_tokens: usize,
+
+    /// Which findings are sent first when the budget can't cover them all
+    #[serde(default)]
+    pub prioritization: AiPrioritizationConfig,
+}
+
+/// How AI enrichment ranks eligible findings (`[ai.prioritization]`)
+///
+/// Findings are sent highest score first until `max_cost_per_run` would be
+/// exceeded; the rest are skipped for budget.
+///
+/// ```toml
+/// [ai.prioritization]
+/// severity = 40             # errors score the full weight, warnings half
+/// confidence = 20           # high confidence full, medium half, low none
+/// critical_path = 20        # under a [risk] critical_paths glob
+/// dependents = 10           # log-scaled, saturates at 255 dependents
+/// new = 10                  # not in the baseline
+/// only_new = false          # send only findings not in the baseline
+/// min_severity = "warning"  # "error" sends errors only
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AiPrioritizationConfig {
+    pub severity: f64,
+    pub confidence: f64,
+    pub critical_path: f64,
+    pub dependents: f64,
+    pub new: f64,
+    /// Skip findings the baseline already records (`--ai-only-new`)
+    pub only_new: bool,
+    /// Least severe finding sent: "warning" or "error" (`--ai-min-severity`)
+    pub min_severity: String,
+}
+
+impl Default for AiPrioritizationConfig {
+    fn default() -> Self {
+        Self {
+            severity: 40.0,
+            confidence: 20.0,
+            critical_path: 20.0,
+            dependents: 10.0,
+            new: 10.0,
+            only_new: false,
+            min_severity: "warning".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IgnoreConfig {
+    /// Paths to ignore entirely (glob patterns)
+    #[serde(default = "default_ignore_paths")]
+    pub paths: Vec<String>,
+
+    /// Finding ID prefixes to suppress globally (e.g. ["SEC", "SQL"])
+    #[serde(default)]
+    pub findings: Vec<String>,
+
+    /// Per-path rule suppression: glob pattern → list of finding ID prefixes
+    ///
+    /// Example in .revet.toml:
+    /// ```toml
+    /// [ignore.per_path]
+    /// "**/tests/**" = ["SEC", "SQL"]
+    /// "src/generated/**" = ["*"]
+    /// ```
+    #[serde(default)]
+    pub per_path: std::collections::HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputConfig {
+    /// Default output format
+    #[serde(default = "default_format")]
+    pub format: String,
+
+    /// Enable color output
+    #[serde(default = "default_true")]
+    pub color: bool,
+
+    /// Show evidence snippets
+    #[serde(default = "default_true")]
+    pub show_evidence: bool,
+
+    /// Max findings to display (0 = unlimited)
+    #[serde(default)]
+    pub max_findings: usize,
+
+    /// Mask matched secret values in findings, snippets, logs, and AI prompts
+    #[serde(default = "default_true")]
+    pub redact_secrets: bool,
+
+    /// Language for finding messages in terminal and markdown output
+    /// (`"en"`, `"ja"`). `REVET_LOCALE` overrides it; JSON and SARIF keep
+    /// English messages and add a `localized_message`.
+    #[serde(default = "default_locale")]
+    pub locale: String,
+
+    /// Size limits of a SARIF report written with `--output`
+    #[serde(default)]
+    pub sarif: SarifOutputConfig,
+
+    /// Size limits of a JSON report written with `--output`
+    #[serde(default)]
+    pub json: JsonOutputConfig,
+
+    /// How the JUnit report shows info findings
+    #[serde(default)]
+    pub junit: JunitOutputConfig,
+}
+
+/// `[output.sarif]` settings: the largest SARIF file `--output` writes.
+/// The defaults keep each file within what GitHub code scanning accepts.
+///
+/// ```toml
+/// [output.sarif]
+/// max_results = 25000      # results per file; 0 = no limit
+/// max_bytes   = 10000000   # uncompressed bytes per file; 0 = no limit
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SarifOutputConfig {
+    /// Results per file
+    #[serde(default = "default_sarif_max_results")]
+    pub max_results: usize,
+
+    /// Uncompressed size per file
+    #[serde(default = "default_sarif_max_bytes")]
+    pub max_bytes: u64,
+}
+
+fn default_sarif_max_results() -> usize {
+    25_000
+}
+
+fn default_sarif_max_bytes() -> u64 {
+    10_000_000
+}
+
+impl Default for SarifOutputConfig {
+    fn default() -> Self {
+        Self {
+            max_results: default_sarif_max_results(),
+            max_bytes: default_sarif_max_bytes(),
+        }
+    }
+}
+
+/// `[output.json]` settings: the largest JSON file `--output` writes
+/// (default: no limit)
+///
+/// ```toml
+/// [output.json]
+/// max_results = 50000
+/// max_bytes   = 50000000
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonOutputConfig {
+    /// Findings per file; 0 = no limit
+    #[serde(default)]
+    pub max_results: usize,
+
+    /// Uncompressed size per file; 0 = no limit
+    #[serde(default)]
+    pub max_bytes: u64,
+}
+
+/// `[output.junit]` settings: what the JUnit report makes of a file with
+/// only info findings, so that informational output does not keep the
+/// suite red
+///
+/// ```toml
+/// [output.junit]
+/// info = "passed"   # "passed" (default) | "skipped"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JunitOutputConfig {
+    /// `"passed"`: a passed testcase; `"skipped"`: a skipped one
+    #[serde(default = "default_junit_info")]
+    pub info: String,
+}
+
+fn default_junit_info() -> String {
+    "passed".to_string()
+}
+
+impl Default for JunitOutputConfig {
+    fn default() -> Self {
+        Self {
+            info: default_junit_info(),
+        }
+    }
+}
+
+// Default functions
+fn default_diff_base() -> String {
+    "main".to_string()
+}
+
+fn default_locale() -> String {
+    "en".to_string()
+}
+
+fn default_fail_on() -> String {
+    "error".to_string()
+}
+
+fn default_complexity_threshold() -> usize {
+    10
+}
+
+fn default_duplication_min_lines() -> usize {
+    6
+}
+
+fn default_call_graph_depth() -> usize {
+    3
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_provider() -> String {
+    "anthropic".to_string()
+}
+
+fn default_model() -> String {
+    "claude-sonnet-4-20250514".to_string()
+}
+
+fn default_max_cost() -> f64 {
+    1.0
+}
+
+fn default_context_tokens() -> usize {
+    crate::ai::context::DEFAULT_CONTEXT_TOKENS
+}
+
+fn default_ignore_paths() -> Vec<String> {
+    vec![
+        "vendor/".to_string(),
+        "node_modules/".to_string(),
+        "dist/".to_string(),
+        ".git/".to_string(),
+        "__pycache__/".to_string(),
+        ".venv/".to_string(),
+        "venv/".to_string(),
+        "env/".to_string(),
+        "site-packages/".to_string(),
+        "build/".to_string(),
+        "target/".to_string(),
+        ".tox/".to_string(),
+        ".eggs/".to_string(),
+        ".mypy_cache/".to_string(),
+        ".pytest_cache/".to_string(),
+        ".revet-cache/".to_string(),
+    ]
+}
+
+fn default_format() -> String {
+    "terminal".to_string()
+}
+
+impl Default for RevetConfig {
+    fn default() -> Self {
+        toml::from_str("").expect("empty TOML should parse to defaults")
+    }
+}
+
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        Self {
+            languages: Vec::new(),
+            diff_base: default_diff_base(),
+            fail_on: default_fail_on(),
+            profile: None,
+        }
+    }
+}
+
+impl ModulesConfig {
+    /// On/off module names, as written in `[modules]`
+    pub const NAMES: &'static [&'static str] = &[
+        "ml",
+        "security",
+        "infra",
+        "react",
+        "async_patterns",
+        "dependency",
+        "error_handling",
+        "dead_code",
+        "cycles",
+        "complexity",
+        "dead_imports",
+        "toolchain",
+        "hardcoded_endpoints",
+        "magic_numbers",
+        "resource_leaks",
+        "kubernetes",
+        "skew",
+        "hotspots",
+        "ownership",
+        "refactor",
+        "i18n",
+        "feature_flags",
+        "debug_artifacts",
+        "test_quality",
+        "copy",
+        "numeric",
+        "pagination",
+        "a11y",
+        "websec",
+        "contract",
+        "repository",
+        "upgrade",
+        "test_coverage",
+        "coupling",
+        "docs",
+        "concurrency",
+        "duplication",
+        "suppressions",
+        "config_drift",
+    ];
+
+    /// Switch a module on or off by name (`dead_code` or `dead-code`).
+    /// Returns false for an unknown name.
+    pub fn set(&mut self, name: &str, enabled: bool) -> bool {
+        let flag = match name.replace('-', "_").as_str() {
+            "ml" => &mut self.ml,
+            "security" => &mut self.security,
+            "infra" => &mut self.infra,
+            "react" => &mut self.react,
+            "async_patterns" => &mut self.async_patterns,
+            "dependency" => &mut self.dependency,
+            "error_handling" => &mut self.error_handling,
+            "dead_code" => &mut self.dead_code,
+            "cycles" => &mut self.cycles,
+            "complexity" => &mut self.complexity,
+            "dead_imports" => &mut self.dead_imports,
+            "toolchain" => &mut self.toolchain,
+            "hardcoded_endpoints" => &mut self.hardcoded_endpoints,
+            "magic_numbers" => &mut self.magic_numbers,
+            "resource_leaks" => &mut self.resource_leaks,
+            "kubernetes" => &mut self.kubernetes,
+            "skew" => &mut self.skew,
+            "hotspots" => &mut self.hotspots,
+            "ownership" => &mut self.ownership,
+            "refactor" => &mut self.refactor,
+            "i18n" => &mut self.i18n,
+            "feature_flags" => &mut self.feature_flags,
+            "debug_artifacts" => &mut self.debug_artifacts,
+            "test_quality" => &mut self.test_quality,
+            "copy" => &mut self.copy,
+            "numeric" => &mut self.numeric,
+            "pagination" => &mut self.pagination,
+            "a11y" => &mut self.a11y,
+            "websec" => &mut self.websec,
+            "contract" => &mut self.contract,
+            "repository" => &mut self.repository,
+            "upgrade" => &mut self.upgrade,
+            "test_coverage" => &mut self.test_coverage,
+            "coupling" => &mut self.coupling,
+            "docs" => &mut self.docs,
+            "concurrency" => &mut self.concurrency,
+            "duplication" => &mut self.duplication,
+            "suppressions" => &mut self.suppressions,
+            "config_drift" => &mut self.config_drift,
+            _ => return false,
+        };
+        *flag = enabled;
+        true
+    }
+}
+
+impl Default for ModulesConfig {
+    fn default() -> Self {
+        Self {
+            ml: true,
+            security: true,
+            infra: false,
+            react: false,
+            async_patterns: false,
+            dependency: false,
+            dependency_advisories: None,
+            error_handling: false,
+            dead_code: false,
+            cycles: true,
+            complexity: false,
+            complexity_threshold: 10,
+            dead_imports: false,
+            toolchain: false,
+            hardcoded_endpoints: false,
+            magic_numbers: false,
+            resource_leaks: false,
+            kubernetes: false,
+            skew: false,
+            hotspots: false,
+            ownership: false,
+            refactor: false,
+            i18n: false,
+            feature_flags: false,
+            debug_artifacts: false,
+            test_quality: false,
+            copy: false,
+            numeric: false,
+            pagination: false,
+            a11y: false,
+            websec: false,
+            contract: false,
+            repository: false,
+            upgrade: true,
+            test_coverage: false,
+            coupling: false,
+            docs: false,
+            concurrency: false,
+            duplication: false,
+            suppressions: true,
+            config_drift: true,
+            duplication_min_lines: default_duplication_min_lines(),
+            call_graph_depth: default_call_graph_depth(),
+            module_configs: HashMap::new(),
+        }
+    }
+}
+
+impl Default for AIConfig {
+    fn default() -> Self {
+        Self {
+            provider: default_provider(),
+            model: default_model(),
+            api_key: None,
+            max_cost_per_run: default_max_cost(),
+            base_url: None,
+            context_tokens: default_context_tokens(),
+            prioritization: AiPrioritizationConfig::default(),
+        }
+    }
+}
+
+impl Default for IgnoreConfig {
+    fn default() -> Self {
+        Self {
+            paths: default_ignore_paths(),
+            findings: Vec::new(),
+            per_path: std::collections::HashMap::new(),
+        }
+    }
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            format: default_format(),
+            color: true,
+            show_evidence: true,
+            max_findings: 0,
+            redact_secrets: true,
+            locale: default_locale(),
+            sarif: SarifOutputConfig::default(),
+            json: JsonOutputConfig::default(),
+            junit: JunitOutputConfig::default(),
+        }
+    }
+}
+
+/// A `.revet.toml` as written, for findings that point at one of its lines
+#[derive(Debug, Clone)]
+pub struct ConfigFile {
+    /// Where the file is, or was at the ref it was read from
+    pub path: PathBuf,
+    /// `None` when the text is not valid TOML
+    doc: Option<toml_edit::ImDocument<String>>,
+}
+
+impl ConfigFile {
+    pub fn new(path: PathBuf, text: String) -> Self {
+        Self {
+            path,
+            doc: toml_edit::ImDocument::parse(text).ok(),
+        }
+    }
+
+    /// Read the file at `path`; `None` if it can't be read
+    pub fn read(path: &Path) -> Option<Self> {
+        let text = std::fs::read_to_string(path).ok()?;
+        Some(Self::new(path.to_path_buf(), text))
+    }
+
+    /// 1-based line of the key at `keys`, tables first:
+    /// `["modules", "security"]` is `security` in `[modules]`, however the
+    /// tables are written
+    pub fn line_of(&self, keys: &[&str]) -> Option<usize> {
+        let (key, _) = self.lookup(keys)?;
+        self.line_at(key.span()?.start)
+    }
+
+    /// 1-based line of `entry` in the array of strings at `keys`
+    pub fn line_of_entry(&self, keys: &[&str], entry: &str) -> Option<usize> {
+        let (_, item) = self.lookup(keys)?;
+        let value = item
+            .as_array()?
+            .iter()
+            .find(|value| value.as_str() == Some(entry))?;
+        self.line_at(value.span()?.start)
+    }
+
+    fn lookup(&self, keys: &[&str]) -> Option<(&toml_edit::Key, &toml_edit::Item)> {
+        let (last, tables) = keys.split_last()?;
+        let mut table: &dyn toml_edit::TableLike = self.doc.as_ref()?.as_table();
+        for key in tables {
+            table = table.get(key)?.as_table_like()?;
+        }
+        table.get_key_value(last)
+    }
+
+    fn line_at(&self, offset: usize) -> Option<usize> {
+        let raw = self.doc.as_ref()?.raw();
+        Some(raw.get(..offset)?.matches('\n').count() + 1)
+    }
+}
+
+impl RevetConfig {
+    /// Load configuration from a file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        Self::parse(&contents, path)
+    }
+
+    /// Load configuration from `contents`, the text of the file at `path`
+    /// (which need not exist on disk, e.g. when read from a git tree)
+    pub fn parse(contents: &str, path: &Path) -> Result<Self> {
+        let config: RevetConfig = toml::from_str(contents)?;
+        // Custom secret patterns that miss their own examples would silently
+        // under-report, and a misspelt severity would silently not apply, so
+        // both fail the load rather than warn
+        let mut errors = config.analyzers.secrets.check(Some(contents));
+        errors.extend(config.check_severity());
+        if !errors.is_empty() {
+            anyhow::bail!("{}: {}", path.display(), errors.join("\n"));
+        }
+        Ok(config)
+    }
+
+    /// Find and load .revet.toml from the current directory or ancestors
+    pub fn find_and_load(start_dir: &Path) -> Result<Self> {
+        match Self::find_path(start_dir) {
+            Some(config_path) => Self::from_file(&config_path),
+            // No config found, use defaults
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// The nearest `.revet.toml` in `start_dir` or its ancestors
+    pub fn find_path(start_dir: &Path) -> Option<PathBuf> {
+        start_dir
+            .ancestors()
+            .map(|dir| dir.join(".revet.toml"))
+            .find(|path| path.exists())
+    }
+
+    /// The `[severity]` override of finding `id` of analyzer `prefix`: the
+    /// entry for the ID, else the one for the prefix
+    pub fn severity_override(&self, id: &str, prefix: &str) -> Option<SeverityOverride> {
+        self.severity
+            .get(id)
+            .or_else(|| self.severity.get(prefix))
+            .and_then(|value| SeverityOverride::parse(value))
+    }
+
+    /// Errors for `[severity]` entries that are not a known severity
+    fn check_severity(&self) -> Vec<String> {
+        self.severity
+            .iter()
+            .filter(|(_, value)| SeverityOverride::parse(value).is_none())
+            .map(|(key, value)| {
+                format!(
+                    "[severity] {:?} = {:?} is invalid. Must be: error, warning, info, off",
+                    key, value
+                )
+            })
+            .collect()
+    }
+
+    /// Sub-rule selection for `module` (`[analyzers.<module>]`)
+    pub fn rule_selection(&self, module: &str) -> &RuleSelection {
+        match module {
+            "skew" => &self.analyzers.skew.selection,
+            "complexity" => &self.analyzers.complexity.selection,
+            "i18n" => &self.analyzers.i18n.selection,
+            "feature_flags" => &self.analyzers.feature_flags.selection,
+            "debug_artifacts" => &self.analyzers.debug_artifacts.selection,
+            "test_quality" => &self.analyzers.test_quality.selection,
+            "copy" => &self.analyzers.copy.selection,
+            "numeric" => &self.analyzers.numeric.selection,
+            "websec" => &self.analyzers.websec.selection,
+            "contract" => &self.analyzers.contract.selection,
+            "repository" => &self.analyzers.repository.selection,
+            "coupling" => &self.analyzers.coupling.selection,
+            "docs" => &self.analyzers.docs.selection,
+            "concurrency" => &self.analyzers.concurrency.selection,
+            _ => self.analyzers.rules.get(module).unwrap_or(&ALL_RULES),
+        }
+    }
+
+    /// Save configuration to a file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = toml::to_string_pretty(self)?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Validate the configuration and return lists of errors and warnings.
+    ///
+    /// Errors are fatal (bad values); warnings are advisory (surprising but legal).
+    /// An empty errors list means the config is valid.
+    pub fn validate(&self) -> (Vec<String>, Vec<String>) {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        // [general]
+        let valid_fail_on = ["error", "warning", "info", "never"];
+        if !valid_fail_on.contains(&self.general.fail_on.as_str()) {
+            errors.push(format!(
+                "[general] fail_on = {:?} is invalid. Must be one of: error, warning, info, never",
+                self.general.fail_on
+            ));
+        }
+        if self.general.diff_base.is_empty() {
+            warnings.push("[general] diff_base is empty — defaulting to \"main\"".to_string());
+        }
+
+        // [severity]
+        errors.extend(self.check_severity());
+
+        // [output]
+        let valid_formats = [
+            "terminal",
+            "json",
+            "sarif",
+            "github",
+            "github-matcher",
+            "markdown",
+            "checkstyle",
+            "junit",
+        ];
+        if !valid_formats.contains(&self.output.format.as_str()) {
+            errors.push(format!(
+                "[output] format = {:?} is invalid. Must be one of: terminal, json, sarif, github, github-matcher, markdown, checkstyle, junit",
+                self.output.format
+            ));
+        }
+        if !["passed", "skipped"].contains(&self.output.junit.info.as_str()) {
+            errors.push(format!(
+                "[output.junit] info = {:?} is invalid. Must be one of: passed, skipped",
+                self.output.junit.info
+            ));
+        }
+        if crate::i18n::Locale::parse(&self.output.locale).is_none() {
+            errors.push(format!(
+                "[output] locale = {:?} is invalid. Must be one of: {}",
+                self.output.locale,
+                crate::i18n::Locale::ALL
+                    .iter()
+                    .map(|l| l.code())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        // [ai]
+        let valid_providers = ["anthropic", "openai", "ollama"];
+        if !valid_providers.contains(&self.ai.provider.as_str()) {
+            errors.push(format!(
+                "[ai] provider = {:?} is invalid. Must be one of: anthropic, openai, ollama",
+                self.ai.provider
+            ));
+        }
+
+        // [ai.prioritization]
+        let weights = &self.ai.prioritization;
+        for (key, weight) in [
+            ("severity", weights.severity),
+            ("confidence", weights.confidence),
+            ("critical_path", weights.critical_path),
+            ("dependents", weights.dependents),
+            ("new", weights.new),
+        ] {
+            if !weight.is_finite() || weight < 0.0 {
+                errors.push(format!(
+                    "[ai.prioritization] {} = {} is invalid. Must be a number >= 0",
+                    key, weight
+                ));
+            }
+        }
+        if self.ai.context_tokens < crate::ai::context::MIN_CONTEXT_TOKENS {
+            errors.push(format!(
+                "[ai] context_tokens = {} is too small. Must be at least {}",
+                self.ai.context_tokens,
+                crate::ai::context::MIN_CONTEXT_TOKENS
+            ));
+        }
+        if !matches!(weights.min_severity.as_str(), "error" | "warning") {
+            errors.push(format!(
+                "[ai.prioritization] min_severity = {:?} is invalid. Must be one of: error, warning",
+                weights.min_severity
+            ));
+        }
+
+        // [issues]
+        if !matches!(
+            self.issues.min_severity.as_str(),
+            "error" | "warning" | "info"
+        ) {
+            errors.push(format!(
+                "[issues] min_severity = {:?} is invalid. Must be one of: error, warning, info",
+                self.issues.min_severity
+            ));
+        }
+        if self.issues.min_age_runs == 0 {
+            errors.push("[issues] min_age_runs must be at least 1".to_string());
+        }
+        if self.issues.label.trim().is_empty() {
+            errors.push("[issues] label must not be empty".to_string());
+        }
+
+        // [reporting]
+        if let Some(url) = &self.reporting.webhook_url {
+            if !(url.starts_with("https://") || url.starts_with("http://")) {
+                errors.push(format!(
+                    "[reporting] webhook_url = {:?} is invalid. Must start with https:// or http://",
+                    url
+                ));
+            }
+        }
+        if self.reporting.webhook_timeout_ms == 0 {
+            errors.push("[reporting] webhook_timeout_ms must be at least 1".to_string());
+        }
+        if self.reporting.webhook_secret_env.trim().is_empty() {
+            errors.push("[reporting] webhook_secret_env must not be empty".to_string());
+        }
+
+        // [parser.go]
+        for platform in &self.parser.go.platforms {
+            if crate::parser::go_build::Platform::parse(platform).is_none() {
+                errors.push(format!(
+                    "[parser.go] platforms entry {:?} is invalid. Expected \"GOOS/GOARCH\", e.g. \"linux/amd64\"",
+                    platform
+                ));
+            }
+        }
+
+        // [parser.python]
+        let dotted = |s: &str, wildcard: bool| {
+            !s.is_empty()
+                && s.split('.').all(|part| {
+                    !part.is_empty()
+                        && part
+                            .chars()
+                            .all(|c| c.is_alphanumeric() || c == '_' || (wildcard && c == '*'))
+                })
+        };
+        for (name, framework) in &self.parser.python.frameworks {
+            for decorator in &framework.decorators {
+                if !dotted(decorator, true) {
+                    errors.push(format!(
+                        "[parser.python.frameworks.{}] decorators entry {:?} is invalid. Expected a dotted name without `@` or arguments, e.g. \"app.route\" or \"*.task\"",
+                        name, decorator
+                    ));
+                }
+            }
+            for dependency in &framework.dependencies {
+                if !dotted(dependency, false) {
+                    errors.push(format!(
+                        "[parser.python.frameworks.{}] dependencies entry {:?} is invalid. Expected a dotted name, e.g. \"Depends\"",
+                        name, dependency
+                    ));
+                }
+            }
+        }
+
+        // [parser.csharp]
+        for (name, generator) in &self.parser.csharp.generated {
+            for attribute in generator.attributes.iter().chain(&generator.bases) {
+                if !dotted(attribute, true) {
+                    errors.push(format!(
+                        "[parser.csharp.generated.{}] entry {:?} is invalid. Expected a dotted name without brackets or arguments, e.g. \"GeneratedRegex\" or \"*.*Base\"",
+                        name, attribute
+                    ));
+                }
+            }
+            for member in &generator.members {
+                let literal = member.replace("{name}", "x").replace("{Name}", "X");
+                if !dotted(&literal, false) || literal.contains('.') {
+                    errors.push(format!(
+                        "[parser.csharp.generated.{}] members entry {:?} is invalid. Expected a member name with optional {{name}} / {{Name}} placeholders, e.g. \"{{Name}}Command\"",
+                        name, member
+                    ));
+                }
+            }
+        }
+
+        // [magic_numbers]
+        for (i, pattern) in self.magic_numbers.patterns.iter().enumerate() {
+            let label = format!("[magic_numbers] patterns[{}]", i);
+            if crate::analyzer::magic_numbers::tunables::TunableKind::parse(&pattern.kind).is_none()
+            {
+                errors.push(format!(
+                    "{}: kind = {:?} is invalid. Must be one of: timeout, retries, pool_size, ttl, backoff",
+                    label, pattern.kind
+                ));
+            }
+            if let Some(lang) = &pattern.language {
+                if !["python", "typescript"].contains(&lang.as_str()) {
+                    errors.push(format!(
+                        "{}: language = {:?} is invalid. Must be: python, typescript",
+                        label, lang
+                    ));
+                }
+            }
+            if let Some(unit) = &pattern.unit {
+                if crate::analyzer::magic_numbers::tunables::Unit::parse(unit).is_none() {
+                    errors.push(format!(
+                        "{}: unit = {:?} is invalid. Must be one of: ms, s, min, h",
+                        label, unit
+                    ));
+                }
+            }
+            if pattern.parameter.is_empty() {
+                errors.push(format!("{}: parameter must not be empty", label));
+            }
+        }
+
+        // [resource_leaks]
+        for name in &self.resource_leaks.closeable_types {
+            if name.trim().is_empty() || name.chars().any(char::is_whitespace) {
+                errors.push(format!(
+                    "[resource_leaks] closeable_types entry {:?} must be a type name",
+                    name
+                ));
+            }
+        }
+
+        // [sensitive_logging]
+        for (key, names) in [
+            ("sensitive_names", &self.sensitive_logging.sensitive_names),
+            ("allow_names", &self.sensitive_logging.allow_names),
+        ] {
+            for name in names {
+                if name.trim().is_empty() {
+                    errors.push(format!(
+                        "[sensitive_logging] {} entries must not be empty",
+                        key
+                    ));
+                }
+            }
+        }
+
+        // [profiles.<name>]
+        for name in self.profiles.keys() {
+            if let Err(e) = crate::profile::ProfileSettings::resolve(Some(name), self) {
+                errors.push(format!("[profiles.{}] {}", name, e));
+            }
+        }
+        if let Some(name) = &self.general.profile {
+            if let Err(e) = crate::profile::ProfileSettings::resolve(Some(name), self) {
+                errors.push(format!("[general] profile: {}", e));
+            }
+        }
+
+        // [[analyzers.generated]]
+        for (i, entry) in self.analyzers.generated.iter().enumerate() {
+            let label = format!("[[analyzers.generated]] entry {}", i);
+            if entry.spec.is_empty() || entry.output_dir.is_empty() {
+                errors.push(format!("{}: spec and output_dir must not be empty", label));
+            }
+            if entry.allow_regenerate && entry.command.is_none() {
+                errors.push(format!(
+                    "{}: allow_regenerate = true requires a command",
+                    label
+                ));
+            }
+        }
+
+        // [analyzers.skew]
+        let skew = &self.analyzers.skew;
+        if skew.max_versions == 0 {
+            errors.push("[analyzers.skew] max_versions must be at least 1".to_string());
+        }
+        for key in &skew.keys {
+            if crate::analyzer::skew::KeySpec::parse(key).is_none() {
+                errors.push(format!(
+                    "[analyzers.skew] keys entry {:?} is invalid. Expected \"file:dotted.key\" with a .json or .toml file, e.g. \"tsconfig.json:compilerOptions.strict\"",
+                    key
+                ));
+            }
+        }
+        for entry in &skew.allow {
+            if crate::analyzer::skew::AllowEntry::parse(entry).is_none() {
+                errors.push(format!(
+                    "[analyzers.skew] allow entry {:?} is invalid. Expected \"name@version\" or \"name@version:path\", e.g. \"react@17:packages/legacy-admin\"",
+                    entry
+                ));
+            }
+        }
+
+        // [analyzers.complexity]
+        let mode = &self.analyzers.complexity.mode;
+        if !matches!(mode.as_str(), "absolute" | "delta") {
+            errors.push(format!(
+                "[analyzers.complexity] mode = {:?} is invalid. Must be one of: absolute, delta",
+                mode
+            ));
+        }
+
+        // [analyzers.hotspots]
+        let hotspots = &self.analyzers.hotspots;
+        if crate::churn::Window::parse(&hotspots.window).is_none() {
+            errors.push(format!(
+                "[analyzers.hotspots] window = {:?} is invalid. Expected a positive number followed by d, w, m or y, e.g. \"90d\"",
+                hotspots.window
+            ));
+        }
+        if !(1..=99).contains(&hotspots.percentile) {
+            errors.push(format!(
+                "[analyzers.hotspots] percentile = {} is invalid. Must be between 1 and 99",
+                hotspots.percentile
+            ));
+        }
+
+        // [analyzers.ownership]
+        let ownership = &self.analyzers.ownership;
+        if crate::churn::Window::parse(&ownership.window).is_none() {
+            errors.push(format!(
+                "[analyzers.ownership] window = {:?} is invalid. Expected a positive number followed by d, w, m or y, e.g. \"180d\"",
+                ownership.window
+            ));
+        }
+        if !(1..=100).contains(&ownership.min_share) {
+            errors.push(format!(
+                "[analyzers.ownership] min_share = {} is invalid. Must be between 1 and 100",
+                ownership.min_share
+            ));
+        }
+        if !matches!(ownership.rollup.as_str(), "file" | "directory") {
+            errors.push(format!(
+                "[analyzers.ownership] rollup = {:?} is invalid. Must be one of: file, directory",
+                ownership.rollup
+            ));
+        }
+        if ownership.active.iter().any(|a| a.trim().is_empty()) {
+            errors.push("[analyzers.ownership] active must not contain empty entries".to_string());
+        }
+
+        // [analyzers.i18n]
+        let i18n = &self.analyzers.i18n;
+        for pattern in &i18n.catalogs {
+            if crate::analyzer::translations::catalog::CatalogPattern::parse(pattern).is_none() {
+                errors.push(format!(
+                    "[analyzers.i18n] catalogs entry {:?} is invalid. Expected a glob with one {{locale}} and at most one {{namespace}}, e.g. \"locales/{{locale}}/{{namespace}}.json\"",
+                    pattern
+                ));
+            }
+        }
+        let frameworks = crate::analyzer::translations::lookups::FRAMEWORKS;
+        for name in &i18n.frameworks {
+            if !frameworks.iter().any(|f| f.name == name.as_str()) {
+                let valid: Vec<&str> = frameworks.iter().map(|f| f.name).collect();
+                errors.push(format!(
+                    "[analyzers.i18n] framework {:?} is unknown. Must be one of: {}",
+                    name,
+                    valid.join(", ")
+                ));
+            }
+        }
+        if i18n.default_locale.trim().is_empty() {
+            errors.push("[analyzers.i18n] default_locale must not be empty".to_string());
+        }
+
+        // [analyzers.feature_flags]
+        let flags = &self.analyzers.feature_flags;
+        let sdks = crate::analyzer::feature_flags::checks::SDKS;
+        for name in &flags.sdks {
+            if !sdks.iter().any(|s| s.name == name.as_str()) {
+                let valid: Vec<&str> = sdks.iter().map(|s| s.name).collect();
+                errors.push(format!(
+                    "[analyzers.feature_flags] sdk {:?} is unknown. Must be one of: {}",
+                    name,
+                    valid.join(", ")
+                ));
+            }
+        }
+        if flags.inventory.trim().is_empty() {
+            errors.push("[analyzers.feature_flags] inventory must not be empty".to_string());
+        }
+
+        // [analyzers.debug_artifacts]
+        let debug = &self.analyzers.debug_artifacts;
+        for pattern in &debug.print_patterns {
+            if let Err(e) = regex::Regex::new(pattern) {
+                errors.push(format!(
+                    "[analyzers.debug_artifacts] print pattern {:?} is invalid: {}",
+                    pattern, e
+                ));
+            }
+        }
+        if debug.comment_lines < 2 {
+            errors.push(format!(
+                "[analyzers.debug_artifacts] comment_lines = {} is invalid. Must be at least 2",
+                debug.comment_lines
+            ));
+        }
+        let debug_rules = crate::analyzer::debug_artifacts::RULES;
+        for (rule, severity) in &debug.severity {
+            if !debug_rules.contains(&rule.as_str()) {
+                errors.push(format!(
+                    "[analyzers.debug_artifacts.severity] unknown sub-rule {:?} (valid: {})",
+                    rule,
+                    debug_rules.join(", ")
+                ));
+            } else if !["error", "warning", "info"].contains(&severity.as_str()) {
+                errors.push(format!(
+                    "[analyzers.debug_artifacts.severity] {} = {:?} is invalid. Must be: error, warning, info",
+                    rule, severity
+                ));
+            }
         }
-    }
-}
 
-impl Default for AIConfig {
-    fn default() -> Self {
-        Self {
-            provider: default_provider(),
-            model: default_model(),
-            api_key: None,
-            max_cost_per_run: default_max_cost(),
-            base_url: None,
+        // [analyzers.test_quality]
+        let tests = &self.analyzers.test_quality;
+        for pattern in &tests.assertion_patterns {
+            if let Err(e) = regex::Regex::new(pattern) {
+                errors.push(format!(
+                    "[analyzers.test_quality] assertion pattern {:?} is invalid: {}",
+                    pattern, e
+                ));
+            }
         }
-    }
-}
-
-impl Default for IgnoreConfig {
-    fn default() -> Self {
-        Self {
-            paths: default_ignore_paths(),
-            findings: Vec::new(),
-            per_path: std::collections::HashMap::new(),
+        let test_rules = crate::analyzer::test_quality::RULES;
+        for (rule, severity) in &tests.severity {
+            if !test_rules.contains(&rule.as_str()) {
+                errors.push(format!(
+                    "[analyzers.test_quality.severity] unknown sub-rule {:?} (valid: {})",
+                    rule,
+                    test_rules.join(", ")
+                ));
+            } else if !["error", "warning", "info"].contains(&severity.as_str()) {
+                errors.push(format!(
+                    "[analyzers.test_quality.severity] {} = {:?} is invalid. Must be: error, warning, info",
+                    rule, severity
+                ));
+            }
         }
-    }
-}
 
-impl Default for OutputConfig {
-    fn default() -> Self {
-        Self {
-            format: default_format(),
-            color: true,
-            show_evidence: true,
-            max_findings: 0,
+        // [analyzers.copy]
+        let copy = &self.analyzers.copy;
+        for glob in &copy.allow_paths {
+            if let Err(e) = glob::Pattern::new(glob) {
+                errors.push(format!(
+                    "[analyzers.copy] allow_paths: invalid glob {:?}: {}",
+                    glob, e
+                ));
+            }
+        }
+        for banned in &copy.banned {
+            if banned.term.trim().is_empty() {
+                errors.push("[[analyzers.copy.banned]] term must not be empty".to_string());
+            }
+            if let Some(severity) = &banned.severity {
+                if !["error", "warning", "info"].contains(&severity.as_str()) {
+                    errors.push(format!(
+                        "[[analyzers.copy.banned]] {:?}: severity {:?} is invalid. Must be: error, warning, info",
+                        banned.term, severity
+                    ));
+                }
+            }
+        }
+        let copy_rules = crate::analyzer::copy::RULES;
+        for (rule, severity) in &copy.severity {
+            if !copy_rules.contains(&rule.as_str()) {
+                errors.push(format!(
+                    "[analyzers.copy.severity] unknown sub-rule {:?} (valid: {})",
+                    rule,
+                    copy_rules.join(", ")
+                ));
+            } else if !["error", "warning", "info"].contains(&severity.as_str()) {
+                errors.push(format!(
+                    "[analyzers.copy.severity] {} = {:?} is invalid. Must be: error, warning, info",
+                    rule, severity
+                ));
+            }
         }
-    }
-}
 
-impl RevetConfig {
-    /// Load configuration from a file
-    pub fn from_file(path: &Path) -> Result<Self> {
-        let contents = std::fs::read_to_string(path)?;
-        let config: RevetConfig = toml::from_str(&contents)?;
-        Ok(config)
-    }
+        // [analyzers.numeric]
+        let numeric = &self.analyzers.numeric;
+        for (list, names) in [
+            ("money_names", &numeric.money_names),
+            ("id_names", &numeric.id_names),
+        ] {
+            if names.iter().any(|n| n.trim().is_empty()) {
+                errors.push(format!(
+                    "[analyzers.numeric] {} must not contain empty names",
+                    list
+                ));
+            }
+        }
+        let numeric_rules = crate::analyzer::numeric::RULES;
+        for (rule, severity) in &numeric.severity {
+            if !numeric_rules.contains(&rule.as_str()) {
+                errors.push(format!(
+                    "[analyzers.numeric.severity] unknown sub-rule {:?} (valid: {})",
+                    rule,
+                    numeric_rules.join(", ")
+                ));
+            } else if !["error", "warning", "info"].contains(&severity.as_str()) {
+                errors.push(format!(
+                    "[analyzers.numeric.severity] {} = {:?} is invalid. Must be: error, warning, info",
+                    rule, severity
+                ));
+            }
+        }
 
-    /// Find and load .revet.toml from the current directory or ancestors
-    pub fn find_and_load(start_dir: &Path) -> Result<Self> {
-        let mut current = start_dir;
+        // [analyzers.pagination]
+        if self
+            .analyzers
+            .pagination
+            .small_tables
+            .iter()
+            .any(|t| t.trim().is_empty())
+        {
+            errors.push(
+                "[analyzers.pagination] small_tables must not contain empty names".to_string(),
+            );
+        }
 
-        loop {
-            let config_path = current.join(".revet.toml");
-            if config_path.exists() {
-                return Self::from_file(&config_path);
+        // [analyzers.websec]
+        let websec_rules = crate::analyzer::websec::RULES;
+        for (rule, severity) in &self.analyzers.websec.severity {
+            if !websec_rules.contains(&rule.as_str()) {
+                errors.push(format!(
+                    "[analyzers.websec.severity] unknown sub-rule {:?} (valid: {})",
+                    rule,
+                    websec_rules.join(", ")
+                ));
+            } else if !["error", "warning", "info"].contains(&severity.as_str()) {
+                errors.push(format!(
+                    "[analyzers.websec.severity] {} = {:?} is invalid. Must be: error, warning, info",
+                    rule, severity
+                ));
             }
+        }
 
-            match current.parent() {
-                Some(parent) => current = parent,
-                None => break,
+        // [analyzers.contract]
+        let contract = &self.analyzers.contract;
+        let contract_rules = crate::analyzer::contract::RULES;
+        for (rule, severity) in &contract.severity {
+            if !contract_rules.contains(&rule.as_str()) {
+                errors.push(format!(
+                    "[analyzers.contract.severity] unknown sub-rule {:?} (valid: {})",
+                    rule,
+                    contract_rules.join(", ")
+                ));
+            } else if !["error", "warning", "info"].contains(&severity.as_str()) {
+                errors.push(format!(
+                    "[analyzers.contract.severity] {} = {:?} is invalid. Must be: error, warning, info",
+                    rule, severity
+                ));
+            }
+        }
+        for base in &contract.base_paths {
+            if !base.starts_with('/') {
+                errors.push(format!(
+                    "[analyzers.contract] base_paths entry {:?} must start with '/'",
+                    base
+                ));
             }
         }
 
-        // No config found, use defaults
-        Ok(Self::default())
-    }
-
-    /// Save configuration to a file
-    pub fn save(&self, path: &Path) -> Result<()> {
-        let contents = toml::to_string_pretty(self)?;
-        std::fs::write(path, contents)?;
-        Ok(())
-    }
-
-    /// Validate the configuration and return lists of errors and warnings.
-    ///
-    /// Errors are fatal (bad values); warnings are advisory (surprising but legal).
-    /// An empty errors list means the config is valid.
-    pub fn validate(&self) -> (Vec<String>, Vec<String>) {
-        let mut errors = Vec::new();
-        let mut warnings = Vec::new();
+        // [analyzers.repository]
+        let repository = &self.analyzers.repository;
+        if repository.stale_baseline_entries == 0 || repository.stale_baseline_days == 0 {
+            errors.push(
+                "[analyzers.repository] stale_baseline_entries and stale_baseline_days must be at least 1"
+                    .to_string(),
+            );
+        }
+        if repository
+            .protected_branch
+            .as_deref()
+            .is_some_and(|b| b.trim().is_empty())
+        {
+            errors.push("[analyzers.repository] protected_branch must not be empty".to_string());
+        }
+        if repository
+            .required_checks
+            .iter()
+            .any(|c| c.trim().is_empty())
+        {
+            errors.push(
+                "[analyzers.repository] required_checks must not contain empty names".to_string(),
+            );
+        }
 
-        // [general]
-        let valid_fail_on = ["error", "warning", "info", "never"];
-        if !valid_fail_on.contains(&self.general.fail_on.as_str()) {
+        // [analyzers.coupling]
+        let coupling = &self.analyzers.coupling;
+        if !coupling.envy_ratio.is_finite() || coupling.envy_ratio < 1.0 {
             errors.push(format!(
-                "[general] fail_on = {:?} is invalid. Must be one of: error, warning, info, never",
-                self.general.fail_on
+                "[analyzers.coupling] envy_ratio = {} is invalid. Must be at least 1",
+                coupling.envy_ratio
             ));
         }
-        if self.general.diff_base.is_empty() {
-            warnings.push("[general] diff_base is empty — defaulting to \"main\"".to_string());
+        if !coupling.intimacy_density.is_finite() || coupling.intimacy_density <= 0.0 {
+            errors.push(format!(
+                "[analyzers.coupling] intimacy_density = {} is invalid. Must be positive",
+                coupling.intimacy_density
+            ));
+        }
+        if coupling.envy_min_refs == 0 || coupling.intimacy_min_edges == 0 {
+            errors.push(
+                "[analyzers.coupling] envy_min_refs and intimacy_min_edges must be at least 1"
+                    .to_string(),
+            );
+        }
+        let coupling_rules = crate::analyzer::coupling::RULES;
+        for (rule, severity) in &coupling.severity {
+            if !coupling_rules.contains(&rule.as_str()) {
+                errors.push(format!(
+                    "[analyzers.coupling.severity] unknown sub-rule {:?} (valid: {})",
+                    rule,
+                    coupling_rules.join(", ")
+                ));
+            } else if !["error", "warning", "info"].contains(&severity.as_str()) {
+                errors.push(format!(
+                    "[analyzers.coupling.severity] {} = {:?} is invalid. Must be: error, warning, info",
+                    rule, severity
+                ));
+            }
         }
 
-        // [output]
-        let valid_formats = ["terminal", "json", "sarif", "github"];
-        if !valid_formats.contains(&self.output.format.as_str()) {
+        // [analyzers.docs]
+        let docs = &self.analyzers.docs;
+        if let Some(min) = docs.min_coverage {
+            if !(0.0..=1.0).contains(&min) {
+                errors.push(format!(
+                    "[analyzers.docs] min_coverage = {} is invalid. Must be between 0 and 1",
+                    min
+                ));
+            }
+        }
+        let docs_rules = crate::analyzer::docs::RULES;
+        for (rule, severity) in &docs.severity {
+            if !docs_rules.contains(&rule.as_str()) {
+                errors.push(format!(
+                    "[analyzers.docs.severity] unknown sub-rule {:?} (valid: {})",
+                    rule,
+                    docs_rules.join(", ")
+                ));
+            } else if !["error", "warning", "info"].contains(&severity.as_str()) {
+                errors.push(format!(
+                    "[analyzers.docs.severity] {} = {:?} is invalid. Must be: error, warning, info",
+                    rule, severity
+                ));
+            }
+        }
+
+        // [analyzers.concurrency]
+        let concurrency = &self.analyzers.concurrency;
+        if concurrency.max_depth == 0 {
+            errors.push("[analyzers.concurrency] max_depth must be at least 1".to_string());
+        }
+        if let Some(empty) = concurrency
+            .entry_points
+            .iter()
+            .find(|e| e.trim_matches('.').is_empty())
+        {
             errors.push(format!(
-                "[output] format = {:?} is invalid. Must be one of: terminal, json, sarif, github",
-                self.output.format
+                "[analyzers.concurrency] entry_points: {:?} is not a call name",
+                empty
             ));
         }
+        let concurrency_rules = crate::analyzer::concurrency::RULES;
+        for (rule, severity) in &concurrency.severity {
+            if !concurrency_rules.contains(&rule.as_str()) {
+                errors.push(format!(
+                    "[analyzers.concurrency.severity] unknown sub-rule {:?} (valid: {})",
+                    rule,
+                    concurrency_rules.join(", ")
+                ));
+            } else if !["error", "warning", "info"].contains(&severity.as_str()) {
+                errors.push(format!(
+                    "[analyzers.concurrency.severity] {} = {:?} is invalid. Must be: error, warning, info",
+                    rule, severity
+                ));
+            }
+        }
 
-        // [ai]
-        let valid_providers = ["anthropic", "openai", "ollama"];
-        if !valid_providers.contains(&self.ai.provider.as_str()) {
+        // [analyzers.secrets]
+        let min_entropy = self.analyzers.secrets.min_entropy;
+        if !min_entropy.is_finite() || min_entropy < 0.0 {
             errors.push(format!(
-                "[ai] provider = {:?} is invalid. Must be one of: anthropic, openai, ollama",
-                self.ai.provider
+                "[analyzers.secrets] min_entropy = {} is invalid. Must be zero or more",
+                min_entropy
             ));
         }
+        errors.extend(self.analyzers.secrets.check(None));
+
+        // [analyzers.<module>]
+        errors.extend(crate::analyzer::rules::check_selections(self));
+
+        // Finding-ID namespaces of [[rules]] and custom secret patterns
+        if let Err(conflicts) = crate::analyzer::registry::Registry::from_config(self) {
+            errors.extend(conflicts);
+        }
 
         // [rules]
         let valid_severities = ["error", "warning", "info"];
@@ -520,6 +4063,258 @@ impl RevetConfig {
             }
         }
 
+        // [[policy]]
+        if let Err(policy_errors) = crate::policy::compile(&self.policies) {
+            errors.extend(policy_errors);
+        }
+
+        // [[vendored]]
+        for (i, vendored) in self.vendored.iter().enumerate() {
+            let path = std::path::Path::new(&vendored.path);
+            if vendored.path.trim_matches('/').is_empty() {
+                errors.push(format!("[[vendored]] #{}: path must not be empty", i + 1));
+            } else if path.is_absolute()
+                || path
+                    .components()
+                    .any(|c| matches!(c, std::path::Component::ParentDir))
+            {
+                errors.push(format!(
+                    "[[vendored]] path = {:?} must be relative to the repository root",
+                    vendored.path
+                ));
+            }
+            if vendored.upstream_ref.as_deref().is_some_and(str::is_empty) {
+                errors.push(format!(
+                    "[[vendored]] {}: upstream_ref must not be empty",
+                    vendored.path
+                ));
+            }
+            if let Some(fallback) = &vendored.fallback {
+                if crate::generated::GeneratedPolicy::parse(fallback).is_none() {
+                    errors.push(format!(
+                        "[[vendored]] {}: fallback = {:?} is invalid. Must be one of: skip, info-only, analyze",
+                        vendored.path, fallback
+                    ));
+                }
+            }
+        }
+        if let Some(path) = crate::vendored::overlapping(&self.vendored) {
+            errors.push(format!(
+                "[[vendored]] path = {:?} is listed twice or inside another vendored path",
+                path
+            ));
+        }
+
+        // [[project]]
+        for (i, project) in self.projects.iter().enumerate() {
+            for path in std::iter::once(&project.path).chain(&project.depends_on) {
+                let relative = std::path::Path::new(path);
+                if path.trim_matches('/').is_empty() {
+                    errors.push(format!("[[project]] #{}: paths must not be empty", i + 1));
+                } else if relative.is_absolute()
+                    || relative
+                        .components()
+                        .any(|c| matches!(c, std::path::Component::ParentDir))
+                {
+                    errors.push(format!(
+                        "[[project]] path {:?} must be relative to the repository root",
+                        path
+                    ));
+                }
+            }
+        }
+
+        // [risk]
+        if self.risk.medium >= self.risk.high || self.risk.high > 100 {
+            errors.push(format!(
+                "[risk] thresholds must satisfy medium < high <= 100 (got medium = {}, high = {})",
+                self.risk.medium, self.risk.high
+            ));
+        }
+        for glob in &self.risk.critical_paths {
+            if let Err(e) = glob::Pattern::new(glob) {
+                errors.push(format!(
+                    "[risk] critical_paths: invalid glob {:?}: {}",
+                    glob, e
+                ));
+            }
+        }
+
+        // [review_estimate]
+        let estimate = &self.review_estimate;
+        let bands = &estimate.bands;
+        if bands.len() != 3 || bands[0] <= 0.0 || bands.windows(2).any(|w| w[0] >= w[1]) {
+            errors.push(format!(
+                "[review_estimate] bands = {:?} is invalid. Must be three ascending positive minutes, e.g. [15, 60, 180]",
+                bands
+            ));
+        }
+        let weights = [
+            ("line_minutes", estimate.line_minutes),
+            ("complexity_weight", estimate.complexity_weight),
+            ("file_minutes", estimate.file_minutes),
+            ("project_minutes", estimate.project_minutes),
+            ("risk_minutes", estimate.risk_minutes),
+            ("error_minutes", estimate.error_minutes),
+            ("warning_minutes", estimate.warning_minutes),
+            ("info_minutes", estimate.info_minutes),
+        ];
+        for (key, weight) in weights {
+            if !(weight >= 0.0 && weight.is_finite()) {
+                errors.push(format!(
+                    "[review_estimate] {} = {} is invalid. Must not be negative",
+                    key, weight
+                ));
+            }
+        }
+
+        // [upgrade]
+        let bundled = crate::analyzer::upgrade::pack::bundled_runtimes();
+        for (runtime, target) in &self.upgrade.targets {
+            if crate::analyzer::upgrade::pack::Version::parse(target).is_none() {
+                errors.push(format!(
+                    "[upgrade] {} = {:?} is not a version (e.g. \"3.12\" or \"20\")",
+                    runtime, target
+                ));
+            } else if self.upgrade.packs.is_empty() && !bundled.contains(runtime) {
+                warnings.push(format!(
+                    "[upgrade] {}: no bundled pack for this runtime (bundled: {}); add one under packs",
+                    runtime,
+                    bundled.join(", ")
+                ));
+            }
+        }
+
+        // [targets]
+        for (key, version) in &self.targets {
+            match crate::language_target::Language::from_key(key) {
+                None => errors.push(format!(
+                    "[targets] unknown language {:?}. Must be one of: {}",
+                    key,
+                    crate::language_target::Language::ALL
+                        .map(|l| l.key())
+                        .join(", ")
+                )),
+                Some(language) if language.parse_version(version).is_none() => {
+                    errors.push(format!(
+                        "[targets] {} = {:?} is not a {} version",
+                        key,
+                        version,
+                        language.name()
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+
+        // [cache]
+        if !["msgpack", "compact"].contains(&self.cache.format.as_str()) {
+            errors.push(format!(
+                "[cache] format = {:?} is invalid. Must be one of: msgpack, compact",
+                self.cache.format
+            ));
+        }
+        if self.cache.full_snapshot_every == 0 {
+            errors.push("[cache] full_snapshot_every must be at least 1".to_string());
+        }
+        if self.cache.keep_last == Some(0) {
+            errors.push(
+                "[cache] keep_last must be at least 1 (the newest snapshot is always kept)"
+                    .to_string(),
+            );
+        }
+        if let Some(url) = &self.cache.remote.url {
+            let bucket = url.strip_prefix("s3://").map(|rest| rest.split('/').next());
+            if !(url.starts_with("https://") || url.starts_with("http://") || bucket.is_some()) {
+                errors.push(format!(
+                    "[cache.remote] url = {:?} is invalid. Must start with https://, http:// or s3://",
+                    url
+                ));
+            } else if bucket == Some(Some("")) {
+                errors.push(format!("[cache.remote] url = {:?} names no bucket", url));
+            }
+        }
+        if self.cache.remote.timeout_ms == 0 {
+            errors.push("[cache.remote] timeout_ms must be at least 1".to_string());
+        }
+
+        // [performance]
+        if self.performance.max_memory_mb == Some(0) {
+            errors.push("[performance] max_memory_mb must be at least 1".to_string());
+        }
+
+        // [calibration]
+        if !(1..=99).contains(&self.calibration.percentile) {
+            errors.push(format!(
+                "[calibration] percentile = {} is invalid. Must be between 1 and 99",
+                self.calibration.percentile
+            ));
+        }
+
+        // [git]
+        if self.git.allow_fetch && self.git.fetch_limit == 0 {
+            warnings.push(
+                "[git] fetch_limit = 0 fetches nothing — set allow_fetch = false instead"
+                    .to_string(),
+            );
+        }
+
+        // [storage] and [baseline]
+        if self.storage.data_dir.as_deref() == Some("") {
+            errors.push("[storage] data_dir must not be empty".to_string());
+        }
+        if self.baseline.path.as_deref() == Some("") {
+            errors.push("[baseline] path must not be empty".to_string());
+        }
+
+        // [files]
+        if crate::generated::GeneratedPolicy::parse(&self.files.generated).is_none() {
+            errors.push(format!(
+                "[files] generated = {:?} is invalid. Must be one of: skip, info-only, analyze",
+                self.files.generated
+            ));
+        }
+        for marker in &self.files.generated_markers {
+            if let Err(e) = regex::Regex::new(marker) {
+                errors.push(format!(
+                    "[files] generated_markers entry {:?} is not a valid regex: {}",
+                    marker, e
+                ));
+            }
+        }
+        if self.files.marker_lines == 0 && !self.files.generated_markers.is_empty() {
+            warnings.push(
+                "[files] marker_lines = 0 — no file will be classified as generated".to_string(),
+            );
+        }
+        for (key, globs) in [
+            ("test_paths", &self.files.test_paths),
+            ("script_paths", &self.files.script_paths),
+        ] {
+            for g in globs {
+                if let Err(e) = glob::Pattern::new(g) {
+                    errors.push(format!(
+                        "[files] {} entry {:?} is not a valid glob: {}",
+                        key, g, e
+                    ));
+                }
+            }
+        }
+        for (kind, severity) in &self.files.max_severity {
+            if crate::code_kind::CodeKind::parse(kind).is_none() {
+                errors.push(format!(
+                    "[files.max_severity] {:?} is not a code kind. Must be one of: production, test, script",
+                    kind
+                ));
+            }
+            if !matches!(severity.as_str(), "error" | "warning" | "info") {
+                errors.push(format!(
+                    "[files.max_severity] {} = {:?} is invalid. Must be one of: error, warning, info",
+                    kind, severity
+                ));
+            }
+        }
+
         // [gate]
         if !self.gate.is_empty() && self.general.fail_on == "never" {
             warnings.push(
@@ -0,0 +1,513 @@
+//! Analyzer coverage ledger
+//!
+//! Zero findings for a directory can mean clean code or code nobody looked
+//! at. The [`CoverageLedger`] records, for every discovered file, which
+//! analyzers processed it and why the others did not. Discovery, the parser
+//! dispatcher and the analyzer dispatcher each write the decisions they make
+//! into it:
+//!
+//! 1. [`discover_files_recorded`](crate::discovery::discover_files_recorded)
+//!    — files dropped for their extension or by `[ignore] paths`
+//! 2. [`ParserDispatcher::record_coverage`](crate::ParserDispatcher::record_coverage)
+//!    — files parsed into the code graph, or that failed to parse
+//! 3. [`AnalyzerDispatcher::record_coverage`](crate::AnalyzerDispatcher::record_coverage)
+//!    and [`record_graph_coverage`](crate::AnalyzerDispatcher::record_graph_coverage)
+//!    — which content and graph analyzers processed each file
+//! 4. [`GeneratedSet::record`](crate::GeneratedSet::record) — files carrying
+//!    a generated-code marker, skipped under `[files] generated = "skip"`
+//! 5. [`VendoredSet::record`](crate::VendoredSet::record) — files under a
+//!    `[[vendored]]` path, skipped when they match upstream; findings on
+//!    upstream lines are counted as they are suppressed
+//!
+//! Every discovered file ends up either analyzed or under exactly one
+//! [`SkipReason`], so the counts in [`CoverageSummary`] always add up.
+
+use crate::git_objects::Degradation;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Above this many files, the full report groups files by directory and
+/// extension instead of listing each one
+pub const FILE_DETAIL_LIMIT: usize = 2000;
+
+/// Extensions listed per skip reason in the one-line summary
+const SUMMARY_EXTENSIONS: usize = 5;
+
+/// Why a discovered file was not analyzed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// No parser or analyzer handles the file's extension or name
+    UnsupportedExtension,
+    /// Matched `[ignore] paths` (or a profile `exclude`)
+    ExcludedByConfig,
+    /// Not valid UTF-8 text (or unreadable), so no analyzer could scan it
+    Unreadable,
+    /// Parsing failed and no content analyzer scanned the file
+    ParseError,
+    /// Every analyzer that handles the file is disabled
+    NoEnabledAnalyzer,
+    /// Carries a generated-code marker and `[files] generated = "skip"`
+    Generated,
+    /// Under a `[[vendored]]` path and unchanged from upstream, or its
+    /// upstream is unknown and the fallback is `skip`
+    VendoredUpstream,
+    /// Outside the cone of a sparse checkout (see
+    /// `--include-sparse-excluded`)
+    SparseExcluded,
+}
+
+impl SkipReason {
+    fn describe(self, count: usize) -> &'static str {
+        match (self, count) {
+            (Self::UnsupportedExtension, _) => "unsupported extension",
+            (Self::ExcludedByConfig, _) => "excluded by config",
+            (Self::Unreadable, _) => "unreadable",
+            (Self::ParseError, 1) => "parse error",
+            (Self::ParseError, _) => "parse errors",
+            (Self::NoEnabledAnalyzer, _) => "no enabled analyzer",
+            (Self::Generated, _) => "generated",
+            (Self::VendoredUpstream, _) => "vendored upstream",
+            (Self::SparseExcluded, _) => "outside the sparse checkout",
+        }
+    }
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.describe(1))
+    }
+}
+
+/// Everything recorded about one file
+#[derive(Debug, Clone, Default, PartialEq)]
+struct FileRecord {
+    /// Dropped during discovery
+    dropped: Option<SkipReason>,
+    /// Finding prefixes of the analyzers that processed the file
+    analyzers: BTreeSet<String>,
+    /// Finding prefixes of analyzers that handle the file but are disabled
+    disabled: BTreeSet<String>,
+    /// Not readable as UTF-8 text
+    unreadable: bool,
+    /// Parsed into the code graph
+    parsed: bool,
+    parse_error: Option<String>,
+    /// Carries a generated-code marker
+    generated: bool,
+    /// Under a `[[vendored]]` path
+    vendored: bool,
+}
+
+impl FileRecord {
+    fn skip_reason(&self) -> Option<SkipReason> {
+        if let Some(reason) = self.dropped {
+            Some(reason)
+        } else if self.unreadable {
+            Some(SkipReason::Unreadable)
+        } else if !self.analyzers.is_empty() {
+            None
+        } else if self.parse_error.is_some() {
+            Some(SkipReason::ParseError)
+        } else {
+            Some(SkipReason::NoEnabledAnalyzer)
+        }
+    }
+}
+
+/// Per-file record of which analyzers ran, threaded through the pipeline
+#[derive(Debug, Clone, Default)]
+pub struct CoverageLedger {
+    files: BTreeMap<PathBuf, FileRecord>,
+    /// Findings suppressed on vendored upstream lines
+    vendored_upstream: usize,
+}
+
+impl CoverageLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a file handed on for analysis
+    pub fn discovered(&mut self, path: &Path) {
+        self.files.entry(path.to_path_buf()).or_default();
+    }
+
+    /// Record a file dropped before analysis
+    pub fn dropped(&mut self, path: &Path, reason: SkipReason) {
+        self.files.entry(path.to_path_buf()).or_default().dropped = Some(reason);
+    }
+
+    /// Record a file classified as generated, whatever the policy
+    pub fn generated(&mut self, path: &Path) {
+        self.files.entry(path.to_path_buf()).or_default().generated = true;
+    }
+
+    /// Record a file under a `[[vendored]]` path
+    pub fn vendored(&mut self, path: &Path) {
+        self.files.entry(path.to_path_buf()).or_default().vendored = true;
+    }
+
+    /// Count findings suppressed because they are on vendored upstream
+    /// lines
+    pub fn vendored_upstream(&mut self, findings: usize) {
+        self.vendored_upstream += findings;
+    }
+
+    /// Record a file that cannot be read as UTF-8 text
+    pub fn unreadable(&mut self, path: &Path) {
+        self.files.entry(path.to_path_buf()).or_default().unreadable = true;
+    }
+
+    /// Record a file parsed into the code graph
+    pub fn parsed(&mut self, path: &Path) {
+        self.files.entry(path.to_path_buf()).or_default().parsed = true;
+    }
+
+    /// Record a file whose parser failed
+    pub fn parse_failed(&mut self, path: &Path, error: impl Into<String>) {
+        self.files
+            .entry(path.to_path_buf())
+            .or_default()
+            .parse_error = Some(error.into());
+    }
+
+    /// Record that the analyzer with finding prefix `prefix` processed `path`
+    pub fn analyzed_by(&mut self, path: &Path, prefix: &str) {
+        let record = self.files.entry(path.to_path_buf()).or_default();
+        record.analyzers.insert(prefix.to_string());
+    }
+
+    /// Record that the analyzer with finding prefix `prefix` handles `path`
+    /// but is disabled
+    pub fn disabled_for(&mut self, path: &Path, prefix: &str) {
+        let record = self.files.entry(path.to_path_buf()).or_default();
+        record.disabled.insert(prefix.to_string());
+    }
+
+    /// Whether `path` was parsed into the code graph
+    pub fn is_parsed(&self, path: &Path) -> bool {
+        self.files.get(path).is_some_and(|r| r.parsed)
+    }
+
+    /// Whether `path` was classified as generated
+    pub fn is_generated(&self, path: &Path) -> bool {
+        self.files.get(path).is_some_and(|r| r.generated)
+    }
+
+    /// Number of discovered files, analyzed or not
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Why `path` was not analyzed; `None` if it was analyzed or never seen
+    pub fn skip_reason(&self, path: &Path) -> Option<SkipReason> {
+        self.files.get(path).and_then(FileRecord::skip_reason)
+    }
+
+    /// Finding prefixes of the analyzers that processed `path`
+    pub fn analyzers(&self, path: &Path) -> Vec<String> {
+        self.files
+            .get(path)
+            .map(|r| r.analyzers.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Aggregate counts
+    pub fn summary(&self) -> CoverageSummary {
+        let mut analyzed = 0;
+        let mut skipped: BTreeMap<SkipReason, (usize, BTreeMap<String, usize>)> = BTreeMap::new();
+        for (path, record) in &self.files {
+            match record.skip_reason() {
+                None => analyzed += 1,
+                Some(reason) => {
+                    let (count, extensions) = skipped.entry(reason).or_default();
+                    *count += 1;
+                    *extensions.entry(extension_label(path)).or_default() += 1;
+                }
+            }
+        }
+
+        let mut skipped: Vec<SkipGroup> = skipped
+            .into_iter()
+            .map(|(reason, (count, extensions))| {
+                let mut extensions: Vec<(String, usize)> = extensions.into_iter().collect();
+                extensions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                SkipGroup {
+                    reason,
+                    count,
+                    extensions: extensions.into_iter().map(|(ext, _)| ext).collect(),
+                }
+            })
+            .collect();
+        skipped.sort_by(|a, b| b.count.cmp(&a.count).then(a.reason.cmp(&b.reason)));
+
+        CoverageSummary {
+            discovered: self.files.len(),
+            analyzed,
+            skipped,
+            generated: self.files.values().filter(|r| r.generated).count(),
+            vendored: self.files.values().filter(|r| r.vendored).count(),
+            vendored_upstream: self.vendored_upstream,
+        }
+    }
+
+    /// Build the report; with `detail`, list every file (or, above
+    /// [`FILE_DETAIL_LIMIT`] files, every directory/extension group).
+    /// Paths are made relative to `repo_root`.
+    pub fn report(&self, repo_root: &Path, detail: bool) -> CoverageReport {
+        let mut report = CoverageReport {
+            summary: self.summary(),
+            files: Vec::new(),
+            groups: Vec::new(),
+            degraded: Vec::new(),
+        };
+        if !detail {
+            return report;
+        }
+
+        let relative = |path: &Path| path.strip_prefix(repo_root).unwrap_or(path).to_path_buf();
+        if self.files.len() <= FILE_DETAIL_LIMIT {
+            report.files = self
+                .files
+                .iter()
+                .map(|(path, record)| FileCoverage {
+                    path: relative(path),
+                    skipped: record.skip_reason(),
+                    analyzers: record.analyzers.iter().cloned().collect(),
+                    disabled: record.disabled.iter().cloned().collect(),
+                    parse_error: record.parse_error.clone(),
+                    generated: record.generated,
+                    vendored: record.vendored,
+                })
+                .collect();
+            return report;
+        }
+
+        let mut groups: BTreeMap<(PathBuf, String), GroupCoverage> = BTreeMap::new();
+        for (path, record) in &self.files {
+            let directory = relative(path).parent().map(Path::to_path_buf);
+            let extension = extension_label(path);
+            let group = groups
+                .entry((directory.clone().unwrap_or_default(), extension.clone()))
+                .or_insert_with(|| GroupCoverage {
+                    directory: directory.unwrap_or_default(),
+                    extension,
+                    ..Default::default()
+                });
+            group.files += 1;
+            match record.skip_reason() {
+                None => group.analyzed += 1,
+                Some(reason) => *group.skipped.entry(reason).or_default() += 1,
+            }
+            if record.generated {
+                group.generated += 1;
+            }
+            if record.vendored {
+                group.vendored += 1;
+            }
+            for prefix in &record.analyzers {
+                *group.analyzers.entry(prefix.clone()).or_default() += 1;
+            }
+        }
+        report.groups = groups.into_values().collect();
+        report
+    }
+}
+
+/// `.ext`, or the file name for extensionless files such as `Dockerfile`
+fn extension_label(path: &Path) -> String {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!(".{}", ext),
+        None => path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+/// Skipped files sharing one reason
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SkipGroup {
+    pub reason: SkipReason,
+    pub count: usize,
+    /// Extensions of the skipped files, most frequent first
+    pub extensions: Vec<String>,
+}
+
+/// Discovered, analyzed and skipped file counts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CoverageSummary {
+    pub discovered: usize,
+    pub analyzed: usize,
+    pub skipped: Vec<SkipGroup>,
+    /// Files classified as generated, analyzed or skipped
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub generated: usize,
+    /// Files under a `[[vendored]]` path, analyzed or skipped
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub vendored: usize,
+    /// Findings suppressed on vendored upstream lines
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub vendored_upstream: usize,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+impl CoverageSummary {
+    pub fn skipped_count(&self) -> usize {
+        self.skipped.iter().map(|g| g.count).sum()
+    }
+}
+
+impl fmt::Display for CoverageSummary {
+    /// `analyzed 1,234 of 1,410 discovered files; 176 skipped — 120
+    /// unsupported extension (.proto, .sql), 40 excluded by config, ...`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "analyzed {} of {} discovered {}",
+            thousands(self.analyzed),
+            thousands(self.discovered),
+            if self.discovered == 1 {
+                "file"
+            } else {
+                "files"
+            }
+        )?;
+        let generated_analyzed = self.generated
+            - self
+                .skipped
+                .iter()
+                .filter(|g| g.reason == SkipReason::Generated)
+                .map(|g| g.count)
+                .sum::<usize>();
+        if generated_analyzed > 0 {
+            write!(f, " ({} generated)", thousands(generated_analyzed))?;
+        }
+        if !self.skipped.is_empty() {
+            write!(f, "; {} skipped \u{2014} ", thousands(self.skipped_count()))?;
+        }
+        for (i, group) in self.skipped.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(
+                f,
+                "{} {}",
+                thousands(group.count),
+                group.reason.describe(group.count)
+            )?;
+            if group.reason == SkipReason::UnsupportedExtension && !group.extensions.is_empty() {
+                let shown: Vec<&str> = group
+                    .extensions
+                    .iter()
+                    .take(SUMMARY_EXTENSIONS)
+                    .map(String::as_str)
+                    .collect();
+                let more = if group.extensions.len() > SUMMARY_EXTENSIONS {
+                    ", \u{2026}"
+                } else {
+                    ""
+                };
+                write!(f, " ({}{})", shown.join(", "), more)?;
+            }
+        }
+        if self.vendored_upstream > 0 {
+            write!(
+                f,
+                "; {} {} on vendored upstream lines suppressed",
+                thousands(self.vendored_upstream),
+                if self.vendored_upstream == 1 {
+                    "finding"
+                } else {
+                    "findings"
+                }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// `1234567` → `1,234,567`
+fn thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// One file in the full report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FileCoverage {
+    pub path: PathBuf,
+    /// Absent when the file was analyzed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<SkipReason>,
+    /// Finding prefixes of the analyzers that processed the file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub analyzers: Vec<String>,
+    /// Analyzers that handle the file but are disabled
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_error: Option<String>,
+    /// Carries a generated-code marker (see `[files] generated`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub generated: bool,
+    /// Under a `[[vendored]]` path
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub vendored: bool,
+}
+
+/// Files of one extension in one directory, in the full report of a large run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GroupCoverage {
+    pub directory: PathBuf,
+    pub extension: String,
+    pub files: usize,
+    pub analyzed: usize,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub generated: usize,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub vendored: usize,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub skipped: BTreeMap<SkipReason, usize>,
+    /// Files processed, per analyzer prefix
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub analyzers: BTreeMap<String, usize>,
+}
+
+/// Coverage section of the output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CoverageReport {
+    #[serde(flatten)]
+    pub summary: CoverageSummary,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<FileCoverage>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<GroupCoverage>,
+    /// Features that skipped objects a partial clone lacks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub degraded: Vec<Degradation>,
+}
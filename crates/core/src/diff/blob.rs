@@ -2,12 +2,18 @@
 //!
 //! This module allows constructing a [`CodeGraph`] from any git ref (branch, tag, commit)
 //! by reading file contents directly from git blobs — no checkout required.
+//!
+//! Blobs are read through [`GitObjects`], so in a partial clone the missing
+//! ones are fetched in one batch when allowed, and skipped and counted
+//! otherwise. Paths a sparse checkout leaves out are not read at all, to
+//! match the live graph.
 
 use anyhow::{Context, Result};
 use git2::{ObjectType, Oid, Repository};
 use rayon::prelude::*;
 use std::path::{Path, PathBuf};
 
+use crate::git_objects::{Feature, GitObjects, MissingBlob};
 use crate::graph::CodeGraph;
 use crate::parser::ParserDispatcher;
 
@@ -23,6 +29,7 @@ pub struct GitFile {
 /// Reads file contents from git trees without checking out
 pub struct GitTreeReader {
     repo: Repository,
+    objects: GitObjects,
 }
 
 impl GitTreeReader {
@@ -30,13 +37,23 @@ impl GitTreeReader {
     pub fn new(repo_path: &Path) -> Result<Self> {
         let repo = Repository::open(repo_path)
             .context("Failed to open git repository for blob reading")?;
-        Ok(Self { repo })
+        let objects = GitObjects::offline(repo_path);
+        Ok(Self { repo, objects })
+    }
+
+    /// Read blobs through the run's `objects`; without them, missing
+    /// blobs of a partial clone are not fetched
+    pub fn with_objects(mut self, objects: GitObjects) -> Self {
+        self.objects = objects;
+        self
     }
 
     /// Read all files at a given ref, filtered by extension
     ///
     /// `extensions` should be in the form `[".py", ".ts", ".js"]`.
-    /// Binary files and non-UTF-8 files are silently skipped.
+    /// Binary files and non-UTF-8 files are silently skipped; files a
+    /// partial clone lacks are skipped and counted as
+    /// [`Feature::RefBaseline`].
     pub fn read_files_at_ref(&self, ref_spec: &str, extensions: &[&str]) -> Result<Vec<GitFile>> {
         let tree = self.resolve_tree(ref_spec)?;
 
@@ -62,14 +79,28 @@ impl GitTreeReader {
             if !has_matching_extension(&rel_path, extensions) {
                 return git2::TreeWalkResult::Ok;
             }
+            if !self
+                .objects
+                .in_checkout(&rel_path.to_string_lossy().replace('\\', "/"))
+            {
+                return git2::TreeWalkResult::Ok;
+            }
 
             entries.push((rel_path, entry.id()));
             git2::TreeWalkResult::Ok
         })?;
 
-        // Pass 2: read blobs
+        // Pass 2: read blobs, fetching the missing ones together
+        let missing = self
+            .objects
+            .ensure(&self.repo, entries.iter().map(|(_, oid)| *oid))?;
         let mut files = Vec::with_capacity(entries.len());
+        let mut unread = 0;
         for (rel_path, oid) in entries {
+            if missing.contains(&oid) {
+                unread += 1;
+                continue;
+            }
             if let Ok(blob) = self.repo.find_blob(oid) {
                 if blob.is_binary() {
                     continue;
@@ -82,13 +113,15 @@ impl GitTreeReader {
                 }
             }
         }
+        self.objects.skip(Feature::RefBaseline, unread);
 
         Ok(files)
     }
 
     /// Read a single file at a given ref
     ///
-    /// Returns `None` if the file doesn't exist at that ref or is binary.
+    /// Returns `None` if the file doesn't exist at that ref or is binary,
+    /// and a [`MissingBlob`] error when a partial clone lacks it.
     pub fn read_file_at_ref(&self, ref_spec: &str, file_path: &Path) -> Result<Option<String>> {
         let tree = self.resolve_tree(ref_spec)?;
 
@@ -98,9 +131,13 @@ impl GitTreeReader {
         };
 
         let blob = self
-            .repo
-            .find_blob(entry.id())
-            .context("Failed to read blob")?;
+            .objects
+            .blob(&self.repo, entry.id(), Feature::RefFiles)
+            .context("Failed to read blob")?
+            .ok_or_else(|| MissingBlob {
+                path: file_path.to_path_buf(),
+                ref_spec: ref_spec.to_string(),
+            })?;
 
         if blob.is_binary() {
             return Ok(None);
@@ -147,6 +184,7 @@ impl GitTreeReader {
         for local_graph in per_file {
             graph.merge(local_graph);
         }
+        graph.assign_fingerprints();
 
         Ok(graph)
     }
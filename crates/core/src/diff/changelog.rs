@@ -0,0 +1,664 @@
+//! Release-notes changelog between two graph snapshots
+//!
+//! Compares the top-level functions, classes and variables of two graphs
+//! (public ones only with [`SymbolFilter::PublicOnly`], using the
+//! unused-exports notion of exported) and sorts every difference into one
+//! category:
+//!
+//! - **signature changes**: a function of the same
+//!   [`NodeFingerprint`] symbol (file, kind and qualified name) whose
+//!   parameters or return type differ
+//! - **moved**: a symbol removed from one file and added to another with the
+//!   same kind, name and signature. When several files qualify, the one
+//!   sharing the longest directory prefix with the old file wins; a tie
+//!   leaves the symbol unmatched.
+//! - **renamed**: a function or class removed and added in the same file
+//!   under another name, whose signature tokens (parameters and return type,
+//!   or bases, methods and fields) overlap by at least
+//!   [`RENAME_SIMILARITY`]
+//! - **added** / **removed**: everything left unmatched
+//! - **structural churn**: files whose node count changed by at least the
+//!   churn threshold (all nodes, whatever the filter)
+//!
+//! Moves and renames are only accepted when the pair is each side's unique
+//! best candidate, so a symbol is never reported twice. Test and script
+//! files are left out.
+//!
+//! Changes are grouped by project: the nearest directory holding a manifest
+//! (see [`MANIFESTS`]), or `.` for the repository root.
+
+use crate::analyzer::unused_exports::{is_exported, is_top_level_symbol};
+use crate::code_kind::CodeKind;
+use crate::graph::{fingerprint, CodeGraph, Node, NodeData, NodeFingerprint, NodeKind};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Node-count change that makes a file's churn worth reporting
+pub const DEFAULT_CHURN_THRESHOLD: usize = 10;
+
+/// Least signature-token overlap (Jaccard) for a rename
+pub const RENAME_SIMILARITY: f64 = 0.75;
+
+/// Files that mark a project directory in a monorepo
+pub const MANIFESTS: &[&str] = &[
+    "package.json",
+    "pyproject.toml",
+    "setup.py",
+    "Cargo.toml",
+    "go.mod",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "composer.json",
+    "Gemfile",
+];
+
+/// Which symbols a changelog covers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SymbolFilter {
+    /// Every top-level function, class and variable
+    #[default]
+    All,
+    /// Exported (public) symbols only
+    PublicOnly,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ChangelogOptions {
+    pub filter: SymbolFilter,
+    pub churn_threshold: usize,
+}
+
+impl Default for ChangelogOptions {
+    fn default() -> Self {
+        Self {
+            filter: SymbolFilter::All,
+            churn_threshold: DEFAULT_CHURN_THRESHOLD,
+        }
+    }
+}
+
+/// A symbol on one side of the changelog
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Symbol {
+    pub name: String,
+    /// `function`, `class` or `variable`
+    pub kind: String,
+    /// Path relative to the repository root
+    pub file: String,
+    pub line: usize,
+    /// `(a: int, b = 1) -> str` for functions, `(Base)` for classes with bases
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    pub public: bool,
+}
+
+/// A function whose parameters or return type changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignatureChange {
+    /// The function as it is in the new snapshot
+    #[serde(flatten)]
+    pub symbol: Symbol,
+    pub old_signature: String,
+}
+
+/// A symbol that moved to another file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Moved {
+    /// The symbol at its new location
+    #[serde(flatten)]
+    pub symbol: Symbol,
+    pub old_file: String,
+    pub old_line: usize,
+}
+
+/// A symbol renamed in place
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Renamed {
+    /// The symbol under its new name
+    #[serde(flatten)]
+    pub symbol: Symbol,
+    pub old_name: String,
+}
+
+/// A file whose node count changed by at least the churn threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Churn {
+    pub file: String,
+    pub old_nodes: usize,
+    pub new_nodes: usize,
+    pub delta: i64,
+}
+
+/// Changes within one project
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectChanges {
+    /// Project directory relative to the repository root (`.` at the root)
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<Symbol>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<Symbol>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signature_changes: Vec<SignatureChange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub moved: Vec<Moved>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub renamed: Vec<Renamed>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub churn: Vec<Churn>,
+}
+
+impl ProjectChanges {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Every change between two snapshots, by project
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Changelog {
+    pub old: String,
+    pub new: String,
+    pub projects: Vec<ProjectChanges>,
+}
+
+/// Finds the project a repository-relative path belongs to
+pub struct Projects {
+    root: PathBuf,
+    cache: std::cell::RefCell<HashMap<PathBuf, bool>>,
+}
+
+impl Projects {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            cache: Default::default(),
+        }
+    }
+
+    /// Nearest directory above `file` holding a manifest, or `.`
+    pub fn of(&self, file: &str) -> String {
+        let mut dir = Path::new(file).parent();
+        while let Some(d) = dir.filter(|d| !d.as_os_str().is_empty()) {
+            let is_project = *self
+                .cache
+                .borrow_mut()
+                .entry(d.to_path_buf())
+                .or_insert_with(|| {
+                    MANIFESTS
+                        .iter()
+                        .any(|m| self.root.join(d).join(m).is_file())
+                });
+            if is_project {
+                return d.to_string_lossy().replace('\\', "/");
+            }
+            dir = d.parent();
+        }
+        ".".to_string()
+    }
+}
+
+/// One symbol of a graph, with what matching needs
+struct Entry<'g> {
+    node: &'g Node,
+    fingerprint: NodeFingerprint,
+    rel: String,
+    public: bool,
+}
+
+impl Entry<'_> {
+    fn kind(&self) -> &'static str {
+        match self.node.kind() {
+            NodeKind::Class => "class",
+            NodeKind::Variable => "variable",
+            _ => "function",
+        }
+    }
+
+    fn symbol(&self) -> Symbol {
+        Symbol {
+            name: self.node.name().to_string(),
+            kind: self.kind().to_string(),
+            file: self.rel.clone(),
+            line: self.node.line(),
+            signature: signature(self.node),
+            public: self.public,
+        }
+    }
+
+    /// Tokens compared for renames; empty for variables
+    fn tokens(&self) -> HashSet<String> {
+        match self.node.data() {
+            NodeData::Function {
+                parameters,
+                return_type,
+            } => parameters
+                .iter()
+                .map(|p| format!("{}:{}", p.name, p.param_type.as_deref().unwrap_or("")))
+                .chain(return_type.iter().map(|r| format!("->{}", r)))
+                .collect(),
+            NodeData::Class {
+                base_classes,
+                methods,
+                fields,
+            } => base_classes
+                .iter()
+                .map(|b| format!("base:{}", b))
+                .chain(methods.iter().map(|m| format!("method:{}", m)))
+                .chain(fields.iter().map(|f| format!("field:{}", f)))
+                .collect(),
+            _ => HashSet::new(),
+        }
+    }
+}
+
+/// `(a: int, b = 1) -> str` for functions, `(Base, Mixin)` for classes
+/// with bases, `: T` for typed variables
+pub fn signature(node: &Node) -> Option<String> {
+    match node.data() {
+        NodeData::Function {
+            parameters,
+            return_type,
+        } => {
+            let params: Vec<String> = parameters
+                .iter()
+                .map(|p| {
+                    let mut param = p.name.clone();
+                    if let Some(t) = &p.param_type {
+                        param.push_str(": ");
+                        param.push_str(t);
+                    }
+                    if let Some(d) = &p.default_value {
+                        param.push_str(" = ");
+                        param.push_str(d);
+                    }
+                    param
+                })
+                .collect();
+            let ret = return_type
+                .as_ref()
+                .map(|r| format!(" -> {}", r))
+                .unwrap_or_default();
+            Some(format!("({}){}", params.join(", "), ret))
+        }
+        NodeData::Class { base_classes, .. } if !base_classes.is_empty() => {
+            Some(format!("({})", base_classes.join(", ")))
+        }
+        NodeData::Variable {
+            var_type: Some(t), ..
+        } => Some(format!(": {}", t)),
+        _ => None,
+    }
+}
+
+fn entries(graph: &CodeGraph, filter: SymbolFilter) -> Vec<Entry<'_>> {
+    let production = production_files(graph);
+    let fingerprints = graph.fingerprints();
+    let mut entries: Vec<Entry> = graph
+        .nodes()
+        .filter(|(id, node)| {
+            production.contains(node.file_path())
+                && match filter {
+                    SymbolFilter::All => is_top_level_symbol(graph, *id),
+                    SymbolFilter::PublicOnly => is_exported(graph, *id),
+                }
+        })
+        .map(|(id, node)| Entry {
+            node,
+            fingerprint: fingerprints[id.index()],
+            rel: relative(graph, node.file_path()),
+            public: node.is_public(),
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        (&a.rel, a.node.line(), a.node.name()).cmp(&(&b.rel, b.node.line(), b.node.name()))
+    });
+    entries
+}
+
+/// Files the parser classified as production code
+fn production_files(graph: &CodeGraph) -> HashSet<&PathBuf> {
+    graph
+        .nodes()
+        .filter(|(_, node)| {
+            matches!(
+                node.data(),
+                NodeData::File {
+                    code_kind: CodeKind::Production,
+                    ..
+                }
+            )
+        })
+        .map(|(_, node)| node.file_path())
+        .collect()
+}
+
+fn relative(graph: &CodeGraph, path: &Path) -> String {
+    path.strip_prefix(graph.root_path())
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Directory components `a` and `b` share from the root
+fn proximity(a: &str, b: &str) -> usize {
+    let dirs = |p: &str| {
+        Path::new(p)
+            .parent()
+            .map(|d| {
+                d.components()
+                    .map(|c| c.as_os_str().to_owned())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    };
+    dirs(a)
+        .iter()
+        .zip(dirs(b).iter())
+        .take_while(|(x, y)| x == y)
+        .count()
+}
+
+fn similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Pairs `(removed, added)` that are each other's unique best candidate
+/// under `score` (higher is better; `None` is no candidate)
+fn mutual_best<F>(removed: &[usize], added: &[usize], score: F) -> Vec<(usize, usize)>
+where
+    F: Fn(usize, usize) -> Option<f64>,
+{
+    let best = |among: &[usize], score: &dyn Fn(usize) -> Option<f64>| {
+        let mut scored: Vec<(f64, usize)> = among
+            .iter()
+            .filter_map(|&to| score(to).map(|s| (s, to)))
+            .collect();
+        scored.sort_by(|x, y| y.0.total_cmp(&x.0));
+        match scored.as_slice() {
+            [(s, to), rest @ ..] if rest.first().is_none_or(|(next, _)| next < s) => Some(*to),
+            _ => None,
+        }
+    };
+    removed
+        .iter()
+        .filter_map(|&r| {
+            let a = best(added, &|a| score(r, a))?;
+            (best(removed, &|r2| score(r2, a)) == Some(r)).then_some((r, a))
+        })
+        .collect()
+}
+
+impl Changelog {
+    /// Changes from `old` to `new`, grouped with `projects`
+    pub fn between(
+        old_name: &str,
+        old: &CodeGraph,
+        new_name: &str,
+        new: &CodeGraph,
+        options: &ChangelogOptions,
+        projects: &Projects,
+    ) -> Self {
+        let before = entries(old, options.filter);
+        let after = entries(new, options.filter);
+        let mut groups: BTreeMap<String, ProjectChanges> = BTreeMap::new();
+
+        // Same fingerprint, or the same symbol with another signature
+        let pairs = fingerprint::pair(
+            before.iter().enumerate().map(|(i, e)| (i, &e.fingerprint)),
+            after.iter().enumerate().map(|(i, e)| (i, &e.fingerprint)),
+        );
+        let mut matched_old = vec![false; before.len()];
+        let mut added: Vec<usize> = Vec::new();
+        for (i, o) in pairs {
+            let e = &after[i];
+            let Some(o) = o else {
+                added.push(i);
+                continue;
+            };
+            matched_old[o] = true;
+            let was = &before[o];
+            if matches!(e.node.data(), NodeData::Function { .. })
+                && was.node.data() != e.node.data()
+            {
+                group(&mut groups, projects, &e.rel)
+                    .signature_changes
+                    .push(SignatureChange {
+                        symbol: e.symbol(),
+                        old_signature: signature(was.node).unwrap_or_default(),
+                    });
+            }
+        }
+        let mut removed: Vec<usize> = (0..before.len()).filter(|&o| !matched_old[o]).collect();
+
+        // Moves: same kind, name and signature in another file
+        let moves = mutual_best(&removed, &added, |r, a| {
+            let (r, a) = (&before[r], &after[a]);
+            (r.kind() == a.kind()
+                && r.node.name() == a.node.name()
+                && r.node.data() == a.node.data())
+            .then(|| proximity(&r.rel, &a.rel) as f64)
+        });
+        for &(r, a) in &moves {
+            let (was, now) = (&before[r], &after[a]);
+            group(&mut groups, projects, &now.rel).moved.push(Moved {
+                symbol: now.symbol(),
+                old_file: was.rel.clone(),
+                old_line: was.node.line(),
+            });
+        }
+        removed.retain(|r| !moves.iter().any(|(m, _)| m == r));
+        added.retain(|a| !moves.iter().any(|(_, m)| m == a));
+
+        // Renames: same file and kind, similar signature
+        let tokens_before: HashMap<usize, HashSet<String>> =
+            removed.iter().map(|&r| (r, before[r].tokens())).collect();
+        let tokens_after: HashMap<usize, HashSet<String>> =
+            added.iter().map(|&a| (a, after[a].tokens())).collect();
+        let renames = mutual_best(&removed, &added, |r, a| {
+            let (was, now) = (&before[r], &after[a]);
+            if was.kind() != now.kind() || was.rel != now.rel {
+                return None;
+            }
+            let s = similarity(&tokens_before[&r], &tokens_after[&a]);
+            (s >= RENAME_SIMILARITY).then_some(s)
+        });
+        for &(r, a) in &renames {
+            let now = &after[a];
+            group(&mut groups, projects, &now.rel)
+                .renamed
+                .push(Renamed {
+                    symbol: now.symbol(),
+                    old_name: before[r].node.name().to_string(),
+                });
+        }
+        removed.retain(|r| !renames.iter().any(|(m, _)| m == r));
+        added.retain(|a| !renames.iter().any(|(_, m)| m == a));
+
+        for &a in &added {
+            group(&mut groups, projects, &after[a].rel)
+                .added
+                .push(after[a].symbol());
+        }
+        for &r in &removed {
+            group(&mut groups, projects, &before[r].rel)
+                .removed
+                .push(before[r].symbol());
+        }
+
+        for churn in churn(old, new, options.churn_threshold) {
+            group(&mut groups, projects, &churn.file).churn.push(churn);
+        }
+
+        Changelog {
+            old: old_name.to_string(),
+            new: new_name.to_string(),
+            projects: groups.into_values().collect(),
+        }
+    }
+
+    /// Whether the snapshots differ in nothing the changelog reports
+    pub fn is_empty(&self) -> bool {
+        self.projects.is_empty()
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Grouped markdown with file links relative to the repository root
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# API changes: {} → {}\n", self.old, self.new);
+        if self.is_empty() {
+            out.push_str("\nNo API changes.\n");
+            return out;
+        }
+        let headed = self.projects.len() > 1 || self.projects[0].name != ".";
+        let (h, sub) = if headed { ("##", "###") } else { ("", "##") };
+        for project in &self.projects {
+            if headed {
+                let name = match project.name.as_str() {
+                    "." => "Repository root",
+                    name => name,
+                };
+                out.push_str(&format!("\n{} {}\n", h, name));
+            }
+            let mut section = |title: &str, lines: Vec<String>| {
+                if !lines.is_empty() {
+                    out.push_str(&format!("\n{} {}\n\n", sub, title));
+                    for line in lines {
+                        out.push_str(&format!("- {}\n", line));
+                    }
+                }
+            };
+            section("Added", project.added.iter().map(symbol_line).collect());
+            section("Removed", project.removed.iter().map(symbol_line).collect());
+            section(
+                "Signature changes",
+                project
+                    .signature_changes
+                    .iter()
+                    .map(|c| {
+                        format!(
+                            "`{}` {}: `{}` → `{}`",
+                            c.symbol.name,
+                            link(&c.symbol.file, c.symbol.line),
+                            c.old_signature,
+                            c.symbol.signature.as_deref().unwrap_or("")
+                        )
+                    })
+                    .collect(),
+            );
+            section(
+                "Moved",
+                project
+                    .moved
+                    .iter()
+                    .map(|m| {
+                        format!(
+                            "`{}` ({}): {} → {}",
+                            m.symbol.name,
+                            m.symbol.kind,
+                            link(&m.old_file, m.old_line),
+                            link(&m.symbol.file, m.symbol.line)
+                        )
+                    })
+                    .collect(),
+            );
+            section(
+                "Renamed",
+                project
+                    .renamed
+                    .iter()
+                    .map(|r| {
+                        format!(
+                            "`{}` → `{}` ({}) {}",
+                            r.old_name,
+                            r.symbol.name,
+                            r.symbol.kind,
+                            link(&r.symbol.file, r.symbol.line)
+                        )
+                    })
+                    .collect(),
+            );
+            section(
+                "Structural churn",
+                project
+                    .churn
+                    .iter()
+                    .map(|c| {
+                        format!(
+                            "[{}]({}): {} → {} nodes ({:+})",
+                            c.file, c.file, c.old_nodes, c.new_nodes, c.delta
+                        )
+                    })
+                    .collect(),
+            );
+        }
+        out
+    }
+}
+
+fn group<'a>(
+    groups: &'a mut BTreeMap<String, ProjectChanges>,
+    projects: &Projects,
+    file: &str,
+) -> &'a mut ProjectChanges {
+    let name = projects.of(file);
+    groups
+        .entry(name.clone())
+        .or_insert_with(|| ProjectChanges::new(&name))
+}
+
+fn link(file: &str, line: usize) -> String {
+    format!("[{}:{}]({}#L{})", file, line, file, line)
+}
+
+fn symbol_line(symbol: &Symbol) -> String {
+    format!(
+        "`{}{}` ({}) {}",
+        symbol.name,
+        symbol.signature.as_deref().unwrap_or(""),
+        symbol.kind,
+        link(&symbol.file, symbol.line)
+    )
+}
+
+/// Production files whose node count changed by at least `threshold`
+fn churn(old: &CodeGraph, new: &CodeGraph, threshold: usize) -> Vec<Churn> {
+    let counts = |graph: &CodeGraph| {
+        let production = production_files(graph);
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for (_, node) in graph.nodes() {
+            if node.kind() != &NodeKind::File && production.contains(node.file_path()) {
+                *counts.entry(relative(graph, node.file_path())).or_default() += 1;
+            }
+        }
+        counts
+    };
+    let (before, after) = (counts(old), counts(new));
+    let files: std::collections::BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    files
+        .into_iter()
+        .filter_map(|file| {
+            let old_nodes = before.get(file).copied().unwrap_or(0);
+            let new_nodes = after.get(file).copied().unwrap_or(0);
+            (old_nodes.abs_diff(new_nodes) >= threshold.max(1)).then(|| Churn {
+                file: file.clone(),
+                old_nodes,
+                new_nodes,
+                delta: new_nodes as i64 - old_nodes as i64,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,347 @@
+//! Review effort estimate for a diff — how long it takes to review, and who
+//! should review it
+//!
+//! The estimate sums minutes over signals revet already computes, each
+//! `value × weight` with the weights of `[review_estimate]`:
+//!
+//! | Factor | Value |
+//! |--------|-------|
+//! | `lines` | changed lines, each weighing `1 + complexity_weight × (c − 1)` inside a function of cyclomatic complexity `c`, 1 elsewhere |
+//! | `files` | changed files |
+//! | `projects` | distinct projects touched (see [`Project::of`]) |
+//! | `risk` | the PR risk score (0–100), when impact analysis ran |
+//! | `errors`, `warnings`, `info` | new findings by severity |
+//!
+//! The total picks the band: S below `bands[0]` minutes, M below `bands[1]`,
+//! L below `bands[2]`, XL from there. Every factor is reported with its
+//! value, weight and minutes, so the weights can be calibrated against real
+//! reviews.
+//!
+//! Suggested reviewers are the `CODEOWNERS` owners of the changed files who
+//! also changed them recently: authors of their lines at HEAD (git blame)
+//! with a commit inside the `[analyzers.ownership] window`. An `@user` owner
+//! matches an author email with that local part or the GitHub noreply
+//! address of the user; teams match no one. When no changed file has
+//! owners, the recent authors alone are suggested. The author of the HEAD
+//! commit — the PR author — is left out. Everything is read from the local
+//! repository.
+
+use crate::analyzer::complexity::{function_complexity, FunctionComplexity};
+use crate::analyzer::ownership::codeowners::CodeOwners;
+use crate::authorship::Authorship;
+use crate::churn::Window;
+use crate::config::{RevetConfig, ReviewEstimateConfig};
+use crate::diff::{DiffFileLines, DiffLineMap, RiskReport};
+use crate::finding::ReviewSummary;
+use crate::git_objects::GitObjects;
+use crate::graph::CodeGraph;
+use crate::hash_inputs::Project;
+use crate::storage::StoragePaths;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Review time band
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Band {
+    S,
+    M,
+    L,
+    XL,
+}
+
+impl Band {
+    /// The band of `minutes`, given the minutes where M, L and XL start
+    pub fn of(minutes: f64, bands: &[f64]) -> Self {
+        match bands.iter().filter(|&&start| minutes >= start).count() {
+            0 => Self::S,
+            1 => Self::M,
+            2 => Self::L,
+            _ => Self::XL,
+        }
+    }
+}
+
+impl fmt::Display for Band {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::S => write!(f, "S"),
+            Self::M => write!(f, "M"),
+            Self::L => write!(f, "L"),
+            Self::XL => write!(f, "XL"),
+        }
+    }
+}
+
+/// One term of the estimate: `value × weight = minutes`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Factor {
+    pub name: String,
+    pub value: f64,
+    /// Minutes per unit of `value`
+    pub weight: f64,
+    pub minutes: f64,
+}
+
+/// A suggested reviewer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reviewer {
+    /// Author email
+    pub author: String,
+    /// Changed files the author recently changed
+    pub files: usize,
+    /// Lines of those files last changed by the author
+    pub lines: usize,
+    /// Listed in `CODEOWNERS` for one of those files
+    pub owner: bool,
+}
+
+/// The signals the estimate is computed from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EstimateInputs {
+    pub changed_lines: usize,
+    /// Changed lines weighted by the complexity of their functions
+    pub weighted_lines: f64,
+    pub files: usize,
+    pub projects: usize,
+    /// PR risk score, 0–100
+    pub risk: u8,
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+}
+
+/// Estimated review effort of a diff and its suggested reviewers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewEstimate {
+    pub band: Band,
+    /// Sum of the factors' minutes
+    pub minutes: f64,
+    /// Minutes where the M, L and XL bands start
+    pub bands: Vec<f64>,
+    pub changed_lines: usize,
+    pub factors: Vec<Factor>,
+    #[serde(default)]
+    pub reviewers: Vec<Reviewer>,
+}
+
+impl ReviewEstimate {
+    /// Weigh `inputs` with `config`
+    pub fn compute(inputs: &EstimateInputs, config: &ReviewEstimateConfig) -> Self {
+        let terms = [
+            ("lines", inputs.weighted_lines, config.line_minutes),
+            ("files", inputs.files as f64, config.file_minutes),
+            ("projects", inputs.projects as f64, config.project_minutes),
+            ("risk", inputs.risk as f64, config.risk_minutes),
+            ("errors", inputs.errors as f64, config.error_minutes),
+            ("warnings", inputs.warnings as f64, config.warning_minutes),
+            ("info", inputs.info as f64, config.info_minutes),
+        ];
+        let factors: Vec<Factor> = terms
+            .into_iter()
+            .map(|(name, value, weight)| Factor {
+                name: name.to_string(),
+                value: round2(value),
+                weight,
+                minutes: round2(value * weight),
+            })
+            .collect();
+        let minutes = round2(factors.iter().map(|f| f.minutes).sum());
+        Self {
+            band: Band::of(minutes, &config.bands),
+            minutes,
+            bands: config.bands.clone(),
+            changed_lines: inputs.changed_lines,
+            factors,
+            reviewers: Vec::new(),
+        }
+    }
+
+    /// Estimate the diff `changed` of the repository at `repo_root`: the
+    /// functions of `graph`, the new findings in `summary` and the PR score
+    /// of `risk`, with reviewers from `CODEOWNERS` and the local history
+    /// (blamed through `objects`)
+    #[allow(clippy::too_many_arguments)]
+    pub fn collect(
+        repo_root: &Path,
+        paths: &StoragePaths,
+        config: &RevetConfig,
+        graph: &CodeGraph,
+        changed: &DiffLineMap,
+        summary: &ReviewSummary,
+        risk: Option<&RiskReport>,
+        objects: &GitObjects,
+    ) -> Self {
+        let files: BTreeMap<PathBuf, Vec<usize>> = changed
+            .iter()
+            .map(|(path, lines)| (path.clone(), line_numbers(repo_root, path, lines)))
+            .collect();
+        let functions = function_complexity(graph, config, |file| {
+            file.strip_prefix(repo_root)
+                .is_ok_and(|rel| files.contains_key(rel))
+        });
+        let projects: BTreeSet<String> = files
+            .keys()
+            .map(|path| Project::of(repo_root, path, config))
+            .collect();
+        let inputs = EstimateInputs {
+            changed_lines: files.values().map(Vec::len).sum(),
+            weighted_lines: weighted_lines(
+                repo_root,
+                &files,
+                &functions,
+                config.review_estimate.complexity_weight,
+            ),
+            files: files.len(),
+            projects: projects.len(),
+            risk: risk.map_or(0, |r| r.score),
+            errors: summary.errors,
+            warnings: summary.warnings,
+            info: summary.info,
+        };
+
+        let relative: Vec<String> = files
+            .keys()
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .collect();
+        let window =
+            Window::parse(&config.analyzers.ownership.window).unwrap_or(Window { days: 180 });
+        let reviewers =
+            match Authorship::load_or_compute(repo_root, paths, &relative, window, objects) {
+                Ok(authorship) => suggest_reviewers(
+                    &relative,
+                    &CodeOwners::load(repo_root),
+                    &authorship,
+                    head_author(repo_root).as_deref(),
+                    config.review_estimate.max_reviewers,
+                ),
+                Err(_) => Vec::new(),
+            };
+        Self::compute(&inputs, &config.review_estimate).with_reviewers(reviewers)
+    }
+
+    pub fn with_reviewers(mut self, reviewers: Vec<Reviewer>) -> Self {
+        self.reviewers = reviewers;
+        self
+    }
+}
+
+/// Changed lines of `path`, every line for a new file
+fn line_numbers(repo_root: &Path, path: &Path, lines: &DiffFileLines) -> Vec<usize> {
+    let mut numbers: Vec<usize> = match lines {
+        DiffFileLines::Lines(set) => set.iter().copied().collect(),
+        DiffFileLines::AllNew => std::fs::read_to_string(repo_root.join(path))
+            .map(|content| (1..=content.lines().count()).collect())
+            .unwrap_or_default(),
+    };
+    numbers.sort_unstable();
+    numbers
+}
+
+/// Changed lines (`files`, relative to `repo_root`), each weighing
+/// `1 + complexity_weight × (c − 1)` in the innermost function of
+/// complexity `c` holding it, and 1 outside functions
+pub fn weighted_lines(
+    repo_root: &Path,
+    files: &BTreeMap<PathBuf, Vec<usize>>,
+    functions: &[FunctionComplexity],
+    complexity_weight: f64,
+) -> f64 {
+    let mut total = 0.0;
+    for (path, lines) in files {
+        let file = repo_root.join(path);
+        let in_file: Vec<&FunctionComplexity> =
+            functions.iter().filter(|f| f.file == file).collect();
+        for &line in lines {
+            let innermost = in_file
+                .iter()
+                .filter(|f| f.line <= line && line <= f.end_line)
+                .min_by_key(|f| f.end_line - f.line);
+            total += match innermost {
+                Some(f) => 1.0 + complexity_weight * f.complexity.saturating_sub(1) as f64,
+                None => 1.0,
+            };
+        }
+    }
+    total
+}
+
+/// Up to `max` reviewers for the changed `files` (relative, `/`-separated):
+/// their recent authors, only the `CODEOWNERS` owners among them when any
+/// file has owners, most files and then most lines first. `exclude` is the
+/// PR author's email.
+pub fn suggest_reviewers(
+    files: &[String],
+    owners: &CodeOwners,
+    authorship: &Authorship,
+    exclude: Option<&str>,
+    max: usize,
+) -> Vec<Reviewer> {
+    let mut reviewers: BTreeMap<String, Reviewer> = BTreeMap::new();
+    let mut owned = false;
+    for file in files {
+        let file_owners = owners.owners(file);
+        owned |= !file_owners.is_empty();
+        let Some(authors) = authorship.files.get(file) else {
+            continue;
+        };
+        for (author, &lines) in &authors.lines {
+            let excluded = exclude.is_some_and(|e| e.eq_ignore_ascii_case(author));
+            if excluded || !authorship.active.contains(author) {
+                continue;
+            }
+            let reviewer = reviewers.entry(author.clone()).or_insert_with(|| Reviewer {
+                author: author.clone(),
+                files: 0,
+                lines: 0,
+                owner: false,
+            });
+            reviewer.files += 1;
+            reviewer.lines += lines;
+            reviewer.owner |= file_owners.iter().any(|o| owner_matches(o, author));
+        }
+    }
+    let mut reviewers: Vec<Reviewer> = reviewers
+        .into_values()
+        .filter(|r| r.owner || !owned)
+        .collect();
+    reviewers.sort_by(|a, b| {
+        b.files
+            .cmp(&a.files)
+            .then(b.lines.cmp(&a.lines))
+            .then(a.author.cmp(&b.author))
+    });
+    reviewers.truncate(max);
+    reviewers
+}
+
+/// Whether the `CODEOWNERS` entry `owner` names the author `email`
+fn owner_matches(owner: &str, email: &str) -> bool {
+    let email = email.to_ascii_lowercase();
+    let owner = owner.to_ascii_lowercase();
+    let Some(user) = owner.strip_prefix('@') else {
+        return owner == email;
+    };
+    if user.contains('/') {
+        return false;
+    }
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+    local == user
+        || (domain == "users.noreply.github.com"
+            && local.rsplit_once('+').map_or(local, |(_, name)| name) == user)
+}
+
+/// Email of the HEAD commit's author
+fn head_author(repo_root: &Path) -> Option<String> {
+    let repo = git2::Repository::discover(repo_root).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    let author = commit.author();
+    author.email().map(str::to_string)
+}
+
+fn round2(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
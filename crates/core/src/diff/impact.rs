@@ -99,12 +99,20 @@ impl ImpactAnalysis {
                 .find_by_edge_kind_reverse(new_node_id, EdgeKind::Calls);
 
             // Transitive callers up to the configured depth
-            let transitive_deps = self
+            let (transitive_deps, transitive_depths) = self
                 .new_graph
                 .query()
-                .transitive_callers(new_node_id, Some(self.call_graph_depth));
-
-            report.add_changed_node(new_node_id, classification, direct_deps, transitive_deps);
+                .transitive_callers_with_depth(new_node_id, Some(self.call_graph_depth))
+                .into_iter()
+                .unzip();
+
+            report.add_changed_node(
+                new_node_id,
+                classification,
+                direct_deps,
+                transitive_deps,
+                transitive_depths,
+            );
         }
 
         report
@@ -309,6 +317,7 @@ impl ImpactReport {
         classification: ChangeClassification,
         direct_dependents: Vec<NodeId>,
         transitive_dependents: Vec<NodeId>,
+        transitive_depths: Vec<usize>,
     ) {
         // Update summary before moving values
         let total_affected = direct_dependents.len() + transitive_dependents.len();
@@ -318,6 +327,7 @@ impl ImpactReport {
             classification,
             direct_dependents,
             transitive_dependents,
+            transitive_depths,
         });
 
         // Update summary
@@ -354,6 +364,8 @@ pub struct ChangeImpact {
     pub classification: ChangeClassification,
     pub direct_dependents: Vec<NodeId>,
     pub transitive_dependents: Vec<NodeId>,
+    /// Call-graph depth of each entry in `transitive_dependents` (1 = direct caller)
+    pub transitive_depths: Vec<usize>,
 }
 
 /// Summary statistics for an impact report
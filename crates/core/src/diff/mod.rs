@@ -2,17 +2,26 @@
 
 pub mod blast_radius;
 pub mod blob;
+pub mod changelog;
+pub mod estimate;
 pub mod impact;
+pub mod risk;
+pub mod stack;
 
 pub use blast_radius::{BlastRadiusSummary, RiskLevel};
 pub use blob::GitTreeReader;
+pub use changelog::{Changelog, ChangelogOptions, Projects, SymbolFilter};
+pub use estimate::{Band, ReviewEstimate};
 pub use impact::{ChangeClassification, ChangeImpact, ImpactAnalysis, ImpactReport, ImpactSummary};
+pub use risk::{FileRisk, RiskBreakdown, RiskReport, SymbolRisk};
+pub use stack::StackChange;
 
 use anyhow::{Context, Result};
-use git2::{Diff, DiffOptions, Repository};
+use git2::{Diff, DiffOptions, Patch, Repository};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+use crate::git_objects::{self, Feature, GitObjects};
 use crate::Finding;
 
 /// Which lines in a file were changed
@@ -30,61 +39,79 @@ pub type DiffLineMap = HashMap<PathBuf, DiffFileLines>;
 /// Analyzes git diffs to determine code changes and their impact
 pub struct DiffAnalyzer {
     repo: Repository,
+    objects: GitObjects,
 }
 
 impl DiffAnalyzer {
     /// Create a new diff analyzer for a repository
     pub fn new(repo_path: &Path) -> Result<Self> {
         let repo = Repository::open(repo_path).context("Failed to open git repository")?;
+        let objects = GitObjects::offline(repo_path);
 
-        Ok(Self { repo })
+        Ok(Self { repo, objects })
     }
 
-    /// Get the diff between two commits/refs
+    /// Read base versions through the run's `objects`, so what a partial
+    /// clone lacks is fetched and counted under its policy; without them,
+    /// nothing is fetched
+    pub fn with_objects(mut self, objects: GitObjects) -> Self {
+        self.objects = objects;
+        self
+    }
+
+    /// Name of the branch checked out, or `None` on a detached HEAD
+    pub fn current_branch(&self) -> Option<String> {
+        let head = self.repo.head().ok()?;
+        if !head.is_branch() {
+            return None;
+        }
+        head.shorthand().map(str::to_string)
+    }
+
+    /// Get the diff between two commits/refs, or between `base` and the
+    /// working tree (staged changes included) when `head` is `None`
     pub fn get_diff(&self, base: &str, head: Option<&str>) -> Result<Diff<'_>> {
         let base_tree = self.resolve_tree(base)?;
-        let head_tree = match head {
-            Some(h) => Some(self.resolve_tree(h)?),
-            None => None,
-        };
 
         let mut opts = DiffOptions::new();
         opts.ignore_whitespace(false);
 
-        let diff =
-            self.repo
-                .diff_tree_to_tree(Some(&base_tree), head_tree.as_ref(), Some(&mut opts))?;
+        let diff = match head {
+            Some(h) => {
+                let head_tree = self.resolve_tree(h)?;
+                self.repo
+                    .diff_tree_to_tree(Some(&base_tree), Some(&head_tree), Some(&mut opts))?
+            }
+            None => self
+                .repo
+                .diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut opts))?,
+        };
 
         Ok(diff)
     }
 
-    /// Get changed files from a diff
+    /// Get changed files from a diff. Reads no file contents, so it works
+    /// whatever a partial clone left out.
     pub fn get_changed_files(&self, diff: &Diff) -> Result<Vec<ChangedFile>> {
         let mut changed_files = Vec::new();
 
-        diff.foreach(
-            &mut |delta, _progress| {
-                if let Some(path) = delta.new_file().path() {
-                    let change_type = match delta.status() {
-                        git2::Delta::Added => ChangeType::Added,
-                        git2::Delta::Deleted => ChangeType::Deleted,
-                        git2::Delta::Modified => ChangeType::Modified,
-                        git2::Delta::Renamed => ChangeType::Renamed,
-                        _ => ChangeType::Modified,
-                    };
-
-                    changed_files.push(ChangedFile {
-                        path: path.to_path_buf(),
-                        change_type,
-                        old_path: delta.old_file().path().map(|p| p.to_path_buf()),
-                    });
-                }
-                true
-            },
-            None,
-            None,
-            None,
-        )?;
+        for delta in diff.deltas() {
+            if let Some(path) = delta.new_file().path() {
+                let change_type = match delta.status() {
+                    git2::Delta::Added => ChangeType::Added,
+                    git2::Delta::Deleted => ChangeType::Deleted,
+                    git2::Delta::Modified => ChangeType::Modified,
+                    git2::Delta::Renamed => ChangeType::Renamed,
+                    _ => ChangeType::Modified,
+                };
+
+                changed_files.push(ChangedFile {
+                    path: path.to_path_buf(),
+                    change_type,
+                    old_path: delta.old_file().path().map(|p| p.to_path_buf()),
+                });
+            }
+        }
 
         Ok(changed_files)
     }
@@ -130,7 +157,18 @@ impl DiffAnalyzer {
     /// Added files map to `DiffFileLines::AllNew`, modified files map to specific
     /// line numbers, and deleted files are excluded.
     pub fn get_all_changed_lines(&self, base: &str) -> Result<DiffLineMap> {
-        let diff = self.get_diff(base, None)?;
+        self.changed_lines(base, None)
+    }
+
+    /// Changed lines between `base` and `head`, or the working tree when
+    /// `head` is `None`. In a partial clone, a modified file whose base
+    /// version cannot be fetched counts as wholly changed.
+    pub fn changed_lines(&self, base: &str, head: Option<&str>) -> Result<DiffLineMap> {
+        let diff = self.get_diff(base, head)?;
+        let blobs = git_objects::diff_blobs(&diff, head.is_none());
+        let missing = self
+            .objects
+            .ensure(&self.repo, blobs.values().flatten().copied())?;
 
         let changed = self.get_changed_files(&diff)?;
         let mut map = DiffLineMap::new();
@@ -150,34 +188,102 @@ impl DiffAnalyzer {
             }
         }
 
-        // Second pass: collect added line numbers from hunks
-        diff.foreach(
-            &mut |_delta, _progress| true,
-            None,
-            Some(&mut |_delta, _hunk| true),
-            Some(&mut |delta, _hunk, line| {
-                if line.origin() == '+' {
-                    if let (Some(path), Some(new_lineno)) =
-                        (delta.new_file().path(), line.new_lineno())
-                    {
-                        let path = path.to_path_buf();
-                        if let Some(DiffFileLines::Lines(set)) = map.get_mut(&path) {
-                            set.insert(new_lineno as usize);
-                        }
+        // Second pass: collect added line numbers from hunks, one file at a
+        // time so a missing base only costs that file its line detail
+        let mut unread = 0;
+        for idx in 0..diff.deltas().len() {
+            let Some(path) = diff.get_delta(idx).and_then(|d| d.new_file().path()) else {
+                continue;
+            };
+            let path = path.to_path_buf();
+            let Some(DiffFileLines::Lines(set)) = map.get_mut(&path) else {
+                continue;
+            };
+            let ids = blobs.get(&path).map(Vec::as_slice).unwrap_or_default();
+            if ids.iter().any(|id| missing.contains(id)) {
+                map.insert(path, DiffFileLines::AllNew);
+                unread += 1;
+                continue;
+            }
+            let Some(patch) = Patch::from_diff(&diff, idx)? else {
+                continue;
+            };
+            for hunk in 0..patch.num_hunks() {
+                for line in 0..patch.num_lines_in_hunk(hunk)? {
+                    let line = patch.line_in_hunk(hunk, line)?;
+                    if let ('+', Some(new_lineno)) = (line.origin(), line.new_lineno()) {
+                        set.insert(new_lineno as usize);
                     }
                 }
-                true
-            }),
-        )?;
+            }
+        }
+        self.objects.skip(Feature::ChangedLines, unread);
 
         Ok(map)
     }
 
+    /// The commit `head` (HEAD when `None`) branched off `base` at, so a
+    /// diff against it leaves out what `base` gained since, like
+    /// `git diff base...head`. When `head` is an ancestor of `base` that
+    /// is `head` itself, and the diff has no committed changes.
+    pub fn merge_base(&self, base: &str, head: Option<&str>) -> Result<String> {
+        Ok(self.resolve_merge_base(base, head)?.id().to_string())
+    }
+
     fn resolve_tree(&self, spec: &str) -> Result<git2::Tree<'_>> {
         let obj = self.repo.revparse_single(spec)?;
         let commit = obj.peel_to_commit()?;
         Ok(commit.tree()?)
     }
+
+    fn resolve_merge_base(&self, base: &str, head: Option<&str>) -> Result<git2::Commit<'_>> {
+        let head = head.unwrap_or("HEAD");
+        let (base_commit, head_commit) = (self.resolve_local(base)?, self.resolve_local(head)?);
+        let oid = self
+            .repo
+            .merge_base(base_commit.id(), head_commit.id())
+            .with_context(|| {
+                format!(
+                    "`{}` and `{}` have no common ancestor; in a shallow clone, \
+                     fetch more history (`git fetch --unshallow`)",
+                    base, head
+                )
+            })?;
+        Ok(self.repo.find_commit(oid)?)
+    }
+
+    /// `spec`'s commit, or an error that says how to fetch a ref that is
+    /// not in the local repository
+    fn resolve_local(&self, spec: &str) -> Result<git2::Commit<'_>> {
+        let obj = match self.repo.revparse_single(spec) {
+            Ok(obj) => obj,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => {
+                anyhow::bail!(
+                    "`{}` does not exist in the local repository; fetch it first (`{}`)",
+                    spec,
+                    self.fetch_hint(spec)
+                )
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Ok(obj.peel_to_commit()?)
+    }
+
+    /// The `git fetch` that brings `spec` into the repository: from its
+    /// remote for a remote-tracking ref such as `upstream/main`, else from
+    /// `origin`
+    fn fetch_hint(&self, spec: &str) -> String {
+        let remotes = self.repo.remotes().ok();
+        let remote = spec.split_once('/').filter(|(remote, _)| {
+            remotes
+                .as_ref()
+                .is_some_and(|names| names.iter().flatten().any(|name| name == *remote))
+        });
+        match remote {
+            Some((remote, branch)) => format!("git fetch {} {}", remote, branch),
+            None => format!("git fetch origin {}", spec),
+        }
+    }
 }
 
 /// A file that has been changed
@@ -204,7 +310,8 @@ pub struct LineRange {
     pub end: usize,
 }
 
-/// Filter findings to only those on changed lines.
+/// Filter findings to only those on changed lines. Repository-level
+/// findings are on no line and always kept.
 ///
 /// Returns (kept findings, number filtered out).
 pub fn filter_findings_by_diff(
@@ -216,6 +323,11 @@ pub fn filter_findings_by_diff(
     let mut filtered = 0usize;
 
     for finding in findings {
+        if finding.is_repository_level() {
+            kept.push(finding);
+            continue;
+        }
+
         // Relativize the finding path against repo root
         let rel_path = finding
             .file
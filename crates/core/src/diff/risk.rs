@@ -0,0 +1,300 @@
+//! Risk scoring for changed symbols — fan-in weighted impact.
+//!
+//! Every symbol changed in the diff gets a 0–100 score from three parts:
+//!
+//! | Part | Max | Formula |
+//! |------|-----|---------|
+//! | Fan-in | 50 | `50 × min(1, log2(1 + f) / 8)` — `f` = distinct direct `Calls`/`References` sources |
+//! | Reach | 30 | `30 × min(1, log2(1 + r) / 7)` — `r` = Σ `0.5^(depth − 1)` over transitive callers |
+//! | Critical | 20 | `20` if the symbol or any dependent lives under a `[risk] critical_paths` glob |
+//!
+//! Fan-in saturates at 255 sources and decayed reach at 127, so a helper with
+//! 2 callers scores ~10 while one with 200+ scores near the cap. The total is
+//! rounded to the nearest integer. A file scores as its riskiest changed
+//! symbol, and the PR as its riskiest file; labels come from the `[risk]`
+//! `medium` / `high` thresholds.
+//!
+//! Reach reuses the caller depths recorded by [`ImpactAnalysis`](super::ImpactAnalysis),
+//! and fan-in is counted in a single pass over the graph's edges, so scoring
+//! adds no per-symbol traversal.
+
+use crate::config::RiskConfig;
+use crate::diff::blast_radius::RiskLevel;
+use crate::diff::impact::{ChangeClassification, ImpactReport};
+use crate::graph::{CodeGraph, EdgeKind, NodeId, NodeKind};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Maximum points from direct fan-in
+pub const FAN_IN_WEIGHT: f64 = 50.0;
+/// Maximum points from depth-decayed transitive reach
+pub const REACH_WEIGHT: f64 = 30.0;
+/// Points added when critical code depends on the symbol
+pub const CRITICAL_WEIGHT: f64 = 20.0;
+/// Per-level multiplier applied to transitive callers
+pub const DEPTH_DECAY: f64 = 0.5;
+
+/// `log2(1 + 255)` — fan-in at which the fan-in part saturates
+const FAN_IN_LOG_CAP: f64 = 8.0;
+/// `log2(1 + 127)` — decayed reach at which the reach part saturates
+const REACH_LOG_CAP: f64 = 7.0;
+
+/// Points contributed by each part of the formula
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RiskBreakdown {
+    pub fan_in: f64,
+    pub reach: f64,
+    pub critical: f64,
+    /// Rounded sum, 0–100
+    pub total: u8,
+}
+
+/// Score one symbol from its direct fan-in, decayed reach, and criticality
+pub fn score(direct_fan_in: usize, decayed_reach: f64, critical: bool) -> RiskBreakdown {
+    let part =
+        |value: f64, cap: f64, weight: f64| weight * ((1.0 + value.max(0.0)).log2() / cap).min(1.0);
+    let fan_in = part(direct_fan_in as f64, FAN_IN_LOG_CAP, FAN_IN_WEIGHT);
+    let reach = part(decayed_reach, REACH_LOG_CAP, REACH_WEIGHT);
+    let critical = if critical { CRITICAL_WEIGHT } else { 0.0 };
+    let total = (fan_in + reach + critical).round().clamp(0.0, 100.0) as u8;
+
+    RiskBreakdown {
+        fan_in: round2(fan_in),
+        reach: round2(reach),
+        critical,
+        total,
+    }
+}
+
+/// Sum of `DEPTH_DECAY^(depth − 1)` over callers at the given depths
+pub fn decayed_reach(depths: &[usize]) -> f64 {
+    depths
+        .iter()
+        .map(|&d| DEPTH_DECAY.powi(d.saturating_sub(1) as i32))
+        .sum()
+}
+
+/// Label a score using the configured thresholds
+pub fn level(score: u8, config: &RiskConfig) -> RiskLevel {
+    if score >= config.high {
+        RiskLevel::High
+    } else if score >= config.medium {
+        RiskLevel::Medium
+    } else {
+        RiskLevel::Low
+    }
+}
+
+fn round2(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
+
+/// Risk of one changed symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolRisk {
+    pub name: String,
+    pub kind: NodeKind,
+    /// Path relative to the repo root
+    pub file: PathBuf,
+    pub line: usize,
+    pub classification: ChangeClassification,
+    /// Distinct nodes with a `Calls` or `References` edge to the symbol
+    pub direct_fan_in: usize,
+    /// Transitive callers within the configured call-graph depth
+    pub transitive_dependents: usize,
+    pub decayed_reach: f64,
+    /// The symbol and dependents located under critical paths
+    pub critical_dependents: usize,
+    pub score: u8,
+    pub level: RiskLevel,
+    pub breakdown: RiskBreakdown,
+}
+
+impl SymbolRisk {
+    /// `parse_order()` for functions, the bare name otherwise
+    pub fn display_name(&self) -> String {
+        match self.kind {
+            NodeKind::Function => format!("{}()", self.name),
+            _ => self.name.clone(),
+        }
+    }
+
+    /// Why this symbol scores as it does, e.g. "has 214 direct callers"
+    pub fn reason(&self) -> String {
+        let noun = match (self.kind, self.direct_fan_in) {
+            (NodeKind::Function, 1) => "direct caller",
+            (NodeKind::Function, _) => "direct callers",
+            (_, 1) => "direct reference",
+            _ => "direct references",
+        };
+        let mut reason = format!("has {} {}", self.direct_fan_in, noun);
+        if self.critical_dependents > 0 {
+            reason.push_str(&format!(", {} in critical paths", self.critical_dependents));
+        }
+        reason
+    }
+}
+
+/// Aggregate risk of one changed file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileRisk {
+    /// Path relative to the repo root
+    pub file: PathBuf,
+    /// Score of the riskiest changed symbol in the file
+    pub score: u8,
+    pub level: RiskLevel,
+    /// Number of changed symbols in the file
+    pub symbols: usize,
+    /// Display name of the riskiest symbol
+    pub top_symbol: String,
+}
+
+/// Risk scores for a diff: per symbol, per file, and for the whole PR
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskReport {
+    /// Score of the riskiest changed file
+    pub score: u8,
+    pub level: RiskLevel,
+    /// Sorted by score (highest first), then path
+    pub files: Vec<FileRisk>,
+    /// Sorted by score (highest first), then path, line, and name
+    pub symbols: Vec<SymbolRisk>,
+}
+
+impl RiskReport {
+    /// Score every changed symbol in `report` against the new graph
+    pub fn compute(
+        report: &ImpactReport,
+        graph: &CodeGraph,
+        repo_root: &Path,
+        config: &RiskConfig,
+    ) -> Self {
+        let changes: Vec<_> = report
+            .changes
+            .iter()
+            .filter(|c| graph.node(c.node_id).is_some_and(|n| is_scored(n.kind())))
+            .collect();
+        let changed: HashSet<NodeId> = changes.iter().map(|c| c.node_id).collect();
+
+        // One pass over every edge for all fan-in counts
+        let mut fan_in: HashMap<NodeId, HashSet<NodeId>> = HashMap::new();
+        for (source, _) in graph.nodes() {
+            for (target, edge) in graph.edges_from(source) {
+                if changed.contains(&target)
+                    && matches!(edge.kind(), EdgeKind::Calls | EdgeKind::References)
+                {
+                    fan_in.entry(target).or_default().insert(source);
+                }
+            }
+        }
+
+        let globs: Vec<glob::Pattern> = config
+            .critical_paths
+            .iter()
+            .filter_map(|g| glob::Pattern::new(g).ok())
+            .collect();
+        let relative = |path: &Path| path.strip_prefix(repo_root).unwrap_or(path).to_path_buf();
+        let is_critical = |id: NodeId| {
+            !globs.is_empty()
+                && graph.node(id).is_some_and(|n| {
+                    let rel = relative(n.file_path()).to_string_lossy().replace('\\', "/");
+                    globs.iter().any(|g| g.matches(&rel))
+                })
+        };
+
+        let mut symbols: Vec<SymbolRisk> = changes
+            .iter()
+            .filter_map(|change| {
+                let node = graph.node(change.node_id)?;
+                let direct_fan_in = fan_in.get(&change.node_id).map_or(0, HashSet::len);
+                let reach = decayed_reach(&change.transitive_depths);
+
+                let mut affected: HashSet<NodeId> = HashSet::new();
+                affected.insert(change.node_id);
+                affected.extend(change.direct_dependents.iter().copied());
+                affected.extend(change.transitive_dependents.iter().copied());
+                if let Some(sources) = fan_in.get(&change.node_id) {
+                    affected.extend(sources.iter().copied());
+                }
+                let critical_dependents =
+                    affected.into_iter().filter(|&id| is_critical(id)).count();
+
+                let breakdown = score(direct_fan_in, reach, critical_dependents > 0);
+                Some(SymbolRisk {
+                    name: node.name().to_string(),
+                    kind: *node.kind(),
+                    file: relative(node.file_path()),
+                    line: node.line(),
+                    classification: change.classification,
+                    direct_fan_in,
+                    transitive_dependents: change.transitive_dependents.len(),
+                    decayed_reach: round2(reach),
+                    critical_dependents,
+                    score: breakdown.total,
+                    level: level(breakdown.total, config),
+                    breakdown,
+                })
+            })
+            .collect();
+
+        symbols.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.file.cmp(&b.file))
+                .then_with(|| a.line.cmp(&b.line))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        // Symbols are sorted, so the first one seen per file is its riskiest
+        let mut files: Vec<FileRisk> = Vec::new();
+        for symbol in &symbols {
+            match files.iter_mut().find(|f| f.file == symbol.file) {
+                Some(file) => file.symbols += 1,
+                None => files.push(FileRisk {
+                    file: symbol.file.clone(),
+                    score: symbol.score,
+                    level: symbol.level,
+                    symbols: 1,
+                    top_symbol: symbol.display_name(),
+                }),
+            }
+        }
+
+        let score = files.first().map_or(0, |f| f.score);
+        Self {
+            score,
+            level: level(score, config),
+            files,
+            symbols,
+        }
+    }
+
+    /// The riskiest changed symbol, if any
+    pub fn top(&self) -> Option<&SymbolRisk> {
+        self.symbols.first()
+    }
+
+    /// One-line summary: ``Risk: 72 (high) — `parse_order()` has 214 direct callers``
+    pub fn headline(&self) -> String {
+        let label = self.level.to_string().to_lowercase();
+        match self.top() {
+            Some(top) if top.score > 0 => format!(
+                "Risk: {} ({}) — `{}` {}",
+                self.score,
+                label,
+                top.display_name(),
+                top.reason()
+            ),
+            _ => format!("Risk: {} ({})", self.score, label),
+        }
+    }
+}
+
+/// Node kinds whose changes are scored (not files, modules, or imports)
+fn is_scored(kind: &NodeKind) -> bool {
+    !matches!(
+        kind,
+        NodeKind::File | NodeKind::Module | NodeKind::Import | NodeKind::ConfigReference
+    )
+}
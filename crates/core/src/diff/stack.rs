@@ -0,0 +1,161 @@
+//! Stacked changes — one commit per change, each based on the one before
+//!
+//! Review systems like Gerrit put every change in its own commit, and a
+//! change that depends on another is a commit on top of it. Reviewing the
+//! top of such a stack against the target branch blames the whole stack on
+//! one change; diffing each commit against its parent keeps the findings of
+//! every change separate.
+//!
+//! A change keeps its identity across re-uploads (new patchsets, new
+//! commits) through the `Change-Id` trailer of its commit message, so state
+//! kept per change is keyed on that rather than the commit.
+
+use anyhow::{bail, Context, Result};
+use git2::{Oid, Repository, Sort};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Most commits `--stack` reviews; more usually means the base is not the
+/// branch the stack was built on
+pub const MAX_STACK: usize = 50;
+
+/// Trailer that identifies a change across patchsets
+pub const CHANGE_ID_TRAILER: &str = "Change-Id";
+
+/// One change of a stack
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackChange {
+    /// Commit of the change
+    pub commit: String,
+    /// Its first parent — the change below it, or the base
+    pub parent: String,
+    /// First line of the commit message
+    pub subject: String,
+    /// `Change-Id` trailer, e.g. `I8473b95934b5732ac55d26311a706c9c2bde9940`
+    pub change_id: Option<String>,
+}
+
+impl StackChange {
+    /// Read `commit` (any revision git understands) from the repository at
+    /// `repo_path`
+    pub fn resolve(repo_path: &Path, commit: &str) -> Result<Self> {
+        let repo = Repository::open(repo_path).context("Failed to open git repository")?;
+        let object = repo
+            .revparse_single(commit)
+            .with_context(|| format!("Failed to resolve git ref '{}'", commit))?;
+        let commit = object
+            .peel_to_commit()
+            .with_context(|| format!("'{}' does not point to a commit", commit))?;
+        Self::from_commit(&commit)
+    }
+
+    fn from_commit(commit: &git2::Commit) -> Result<Self> {
+        let parent = commit
+            .parent_id(0)
+            .with_context(|| format!("Commit {} has no parent", commit.id()))?;
+        let message = commit.message().unwrap_or_default();
+        Ok(Self {
+            commit: commit.id().to_string(),
+            parent: parent.to_string(),
+            subject: message.lines().next().unwrap_or_default().to_string(),
+            change_id: change_id(message),
+        })
+    }
+
+    /// What state of this change is kept under: the `Change-Id`, so a new
+    /// patchset finds it, else the commit
+    pub fn key(&self) -> &str {
+        self.change_id.as_deref().unwrap_or(&self.commit)
+    }
+
+    /// Abbreviated commit
+    pub fn short(&self) -> &str {
+        self.commit.get(..7).unwrap_or(&self.commit)
+    }
+
+    /// `abc1234 Subject`
+    pub fn label(&self) -> String {
+        format!("{} {}", self.short(), self.subject)
+    }
+}
+
+/// The `Change-Id` trailer of a commit message: the last one in the final
+/// paragraph, as Gerrit reads it
+pub fn change_id(message: &str) -> Option<String> {
+    let trailers = message.trim_end().rsplit("\n\n").next()?;
+    trailers.lines().rev().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        let value = value.trim();
+        (key.trim().eq_ignore_ascii_case(CHANGE_ID_TRAILER)
+            && value.len() > 1
+            && value.starts_with('I')
+            && value[1..].chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| value.to_string())
+    })
+}
+
+/// The changes from `base` (exclusive) to `head`, oldest first, following
+/// first parents — a merge commit in between is one change
+pub fn walk(repo_path: &Path, base: &str, head: &str) -> Result<Vec<StackChange>> {
+    let repo = Repository::open(repo_path).context("Failed to open git repository")?;
+    let resolve = |spec: &str| -> Result<Oid> {
+        let object = repo
+            .revparse_single(spec)
+            .with_context(|| format!("Failed to resolve git ref '{}'", spec))?;
+        Ok(object.peel_to_commit()?.id())
+    };
+    let (base_id, head_id) = (resolve(base)?, resolve(head)?);
+
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    walk.simplify_first_parent()?;
+    walk.push(head_id)?;
+    walk.hide(base_id)?;
+
+    let mut changes = Vec::new();
+    for id in walk {
+        let commit = repo.find_commit(id?)?;
+        changes.push(StackChange::from_commit(&commit)?);
+        if changes.len() > MAX_STACK {
+            bail!(
+                "{}..{} has more than {} commits; pass the branch the stack is based on",
+                base,
+                head,
+                MAX_STACK
+            );
+        }
+    }
+    Ok(changes)
+}
+
+/// Write the files of `commit` to `dir`, leaving the repository's index
+/// and working tree alone
+pub fn checkout(repo_path: &Path, commit: &str, dir: &Path) -> Result<()> {
+    let repo = Repository::open(repo_path).context("Failed to open git repository")?;
+    let object = repo
+        .revparse_single(commit)
+        .with_context(|| format!("Failed to resolve git ref '{}'", commit))?;
+    let tree = object.peel_to_tree()?;
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let mut opts = git2::build::CheckoutBuilder::new();
+    opts.target_dir(dir).update_index(false).force();
+    repo.checkout_tree(tree.as_object(), Some(&mut opts))
+        .with_context(|| format!("Failed to check out {} to {}", commit, dir.display()))
+}
+
+/// The refs matching `glob` (e.g. `refs/changes/*`), by the commit they
+/// point at; empty when the repository can't be read
+pub fn refs_by_commit(repo_path: &Path, glob: &str) -> HashMap<String, String> {
+    let Ok(repo) = Repository::open(repo_path) else {
+        return HashMap::new();
+    };
+    let Ok(refs) = repo.references_glob(glob) else {
+        return HashMap::new();
+    };
+    refs.flatten()
+        .filter_map(|r| {
+            let commit = r.peel_to_commit().ok()?.id().to_string();
+            Some((commit, r.name()?.to_string()))
+        })
+        .collect()
+}
@@ -2,10 +2,18 @@
 //!
 //! Uses the `ignore` crate (from ripgrep) to automatically respect
 //! `.gitignore`, `.ignore`, and `.git/info/exclude` files.
+//!
+//! Hidden files and directories are skipped, except dotfiles whose name
+//! matches a pattern an analyzer asked for (see
+//! [`Analyzer::extra_dotfiles`](crate::analyzer::Analyzer::extra_dotfiles)).
+//! Those are content-analysis input only: [`is_dotfile`] paths are never
+//! handed to a language parser.
 
+use crate::coverage::{CoverageLedger, SkipReason};
 use anyhow::Result;
 use ignore::overrides::OverrideBuilder;
 use ignore::WalkBuilder;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 /// Discover files under `root` matching any of the given `extensions`,
@@ -16,21 +24,111 @@ pub fn discover_files(
     root: &Path,
     extensions: &[&str],
     ignore_patterns: &[String],
+) -> Result<Vec<PathBuf>> {
+    discover_files_extended(root, extensions, &[], ignore_patterns)
+}
+
+/// Discover files matching extensions OR exact filenames, with gitignore filtering.
+///
+/// Similar to [`discover_files`] but also matches files by exact filename
+/// (e.g., `"Dockerfile"`). Returns absolute paths sorted alphabetically.
+pub fn discover_files_extended(
+    root: &Path,
+    extensions: &[&str],
+    filenames: &[&str],
+    ignore_patterns: &[String],
+) -> Result<Vec<PathBuf>> {
+    discover_files_with_dotfiles(root, extensions, filenames, &[], ignore_patterns)
+}
+
+/// Like [`discover_files_extended`], but also returns hidden files outside
+/// hidden directories whose name matches one of the `dotfiles` globs
+/// (e.g., `".env*"`). Returns absolute paths sorted alphabetically.
+pub fn discover_files_with_dotfiles(
+    root: &Path,
+    extensions: &[&str],
+    filenames: &[&str],
+    dotfiles: &[&str],
+    ignore_patterns: &[String],
+) -> Result<Vec<PathBuf>> {
+    let root = root.canonicalize()?;
+    let dotfiles = DotfileMatcher::new(dotfiles);
+    Ok(walk(&root, ignore_patterns, &dotfiles)?
+        .into_iter()
+        .filter(|path| is_wanted(path, extensions, filenames, &dotfiles))
+        .collect())
+}
+
+/// Like [`discover_files_with_dotfiles`], but records every file it drops in
+/// `ledger`: unsupported extensions, and files under `ignore_patterns`.
+///
+/// Walks the tree twice (with and without the ignore patterns) so excluded
+/// files can be told apart from ones `.gitignore` hides, which are not
+/// counted at all.
+pub fn discover_files_recorded(
+    root: &Path,
+    extensions: &[&str],
+    filenames: &[&str],
+    dotfiles: &[&str],
+    ignore_patterns: &[String],
+    ledger: &mut CoverageLedger,
 ) -> Result<Vec<PathBuf>> {
     let root = root.canonicalize()?;
+    let dotfiles = DotfileMatcher::new(dotfiles);
+    let kept = walk(&root, ignore_patterns, &dotfiles)?;
 
-    let mut builder = WalkBuilder::new(&root);
+    if !ignore_patterns.is_empty() {
+        let kept_set: HashSet<&PathBuf> = kept.iter().collect();
+        for path in walk(&root, &[], &dotfiles)? {
+            if !kept_set.contains(&path) {
+                ledger.dropped(&path, SkipReason::ExcludedByConfig);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    for path in kept {
+        if is_wanted(&path, extensions, filenames, &dotfiles) {
+            ledger.discovered(&path);
+            files.push(path);
+        } else {
+            ledger.dropped(&path, SkipReason::UnsupportedExtension);
+        }
+    }
+    Ok(files)
+}
+
+/// Every file under `root` (absolute, sorted) that `.gitignore` and
+/// `ignore_patterns` leave visible, plus the hidden files `dotfiles` matches
+fn walk(
+    root: &Path,
+    ignore_patterns: &[String],
+    dotfiles: &DotfileMatcher,
+) -> Result<Vec<PathBuf>> {
+    let mut builder = WalkBuilder::new(root);
     builder
-        .hidden(true) // skip hidden files/dirs
+        .hidden(dotfiles.is_empty()) // skip hidden files/dirs
         .git_ignore(true) // respect .gitignore
         .git_global(true) // respect global gitignore
         .git_exclude(true); // respect .git/info/exclude
 
+    if !dotfiles.is_empty() {
+        // Walk hidden entries ourselves: skip hidden directories (`.git`,
+        // `.venv`, ...) and keep only the hidden files an analyzer wants
+        let dotfiles = dotfiles.clone();
+        builder.filter_entry(move |entry| {
+            if entry.depth() == 0 || !is_dotfile(entry.path()) {
+                return true;
+            }
+            entry.file_type().is_some_and(|ft| ft.is_file()) && dotfiles.matches(entry.path())
+        });
+    }
+
     // Add custom ignore patterns from .revet.toml config as overrides.
     // The `ignore` crate uses gitignore syntax for overrides: prefix with `!` to negate.
     // We negate our ignore patterns so they act as excludes.
     if !ignore_patterns.is_empty() {
-        let mut overrides = OverrideBuilder::new(&root);
+        let mut overrides = OverrideBuilder::new(root);
         for pattern in ignore_patterns {
             // Convert directory patterns like "vendor/" to glob "!vendor/**"
             let glob = if pattern.ends_with('/') {
@@ -56,14 +154,12 @@ pub fn discover_files(
             continue;
         }
 
+        // Ensure absolute path
         let path = entry.into_path();
-        if has_supported_extension(&path, extensions) {
-            // Ensure absolute path
-            if path.is_absolute() {
-                files.push(path);
-            } else {
-                files.push(root.join(path));
-            }
+        if path.is_absolute() {
+            files.push(path);
+        } else {
+            files.push(root.join(path));
         }
     }
 
@@ -71,62 +167,59 @@ pub fn discover_files(
     Ok(files)
 }
 
-/// Discover files matching extensions OR exact filenames, with gitignore filtering.
-///
-/// Similar to [`discover_files`] but also matches files by exact filename
-/// (e.g., `"Dockerfile"`). Returns absolute paths sorted alphabetically.
-pub fn discover_files_extended(
-    root: &Path,
+fn is_wanted(
+    path: &Path,
     extensions: &[&str],
     filenames: &[&str],
-    ignore_patterns: &[String],
-) -> Result<Vec<PathBuf>> {
-    let root = root.canonicalize()?;
-
-    let mut builder = WalkBuilder::new(&root);
-    builder
-        .hidden(true)
-        .git_ignore(true)
-        .git_global(true)
-        .git_exclude(true);
-
-    if !ignore_patterns.is_empty() {
-        let mut overrides = OverrideBuilder::new(&root);
-        for pattern in ignore_patterns {
-            let glob = if pattern.ends_with('/') {
-                format!("!{}**", pattern)
-            } else {
-                format!("!{}", pattern)
-            };
-            overrides.add(&glob)?;
-        }
-        builder.overrides(overrides.build()?);
+    dotfiles: &DotfileMatcher,
+) -> bool {
+    if is_dotfile(path) {
+        return dotfiles.matches(path);
     }
+    has_supported_extension(path, extensions) || has_matching_filename(path, filenames)
+}
 
-    let mut files = Vec::new();
+/// Whether the file name of `path` starts with `.` (`.env`, `.npmrc`)
+pub fn is_dotfile(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with('.'))
+}
 
-    for entry in builder.build() {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
+/// Whether `path` is a dotfile whose name matches one of the `patterns`
+/// globs (e.g., `".env*"`). Used for changed-file lists, which bypass the
+/// directory walk.
+pub fn matches_dotfile(path: &Path, patterns: &[&str]) -> bool {
+    DotfileMatcher::new(patterns).matches(path)
+}
 
-        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
-            continue;
-        }
+/// Compiled dotfile name globs
+#[derive(Clone, Default)]
+struct DotfileMatcher {
+    patterns: Vec<glob::Pattern>,
+}
 
-        let path = entry.into_path();
-        if has_supported_extension(&path, extensions) || has_matching_filename(&path, filenames) {
-            if path.is_absolute() {
-                files.push(path);
-            } else {
-                files.push(root.join(path));
-            }
+impl DotfileMatcher {
+    fn new(patterns: &[&str]) -> Self {
+        Self {
+            patterns: patterns
+                .iter()
+                .filter_map(|p| glob::Pattern::new(p).ok())
+                .collect(),
         }
     }
 
-    files.sort();
-    Ok(files)
+    fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        if !is_dotfile(path) {
+            return false;
+        }
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        self.patterns.iter().any(|p| p.matches(name))
+    }
 }
 
 fn has_supported_extension(path: &Path, extensions: &[&str]) -> bool {
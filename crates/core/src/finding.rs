@@ -1,9 +1,18 @@
 //! Finding types that bridge analysis results to output formatters
 
+use crate::analyzer::complexity::delta::DeltaSummary;
+use crate::analyzer::docs::DocCoverage;
+use crate::analyzer::file_timing::TimingReport;
+use crate::code_kind::CodeKind;
 use crate::config::GateConfig;
+use crate::git_objects::Degradation;
+use crate::i18n::{Locale, Msg};
+use crate::memory::MemoryReport;
+use crate::rollout::ShadowModule;
+use crate::schedule::BudgetReport;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Severity level of a finding
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -23,6 +32,36 @@ impl std::fmt::Display for Severity {
     }
 }
 
+/// How likely a finding is to be a true positive.
+///
+/// Analyzers built on name heuristics report `Medium` or `Low`; everything
+/// else defaults to `High`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    Low,
+    Medium,
+    #[default]
+    High,
+}
+
+impl Confidence {
+    pub fn is_high(&self) -> bool {
+        *self == Confidence::High
+    }
+}
+
+impl std::fmt::Display for Confidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Confidence::Low => write!(f, "low"),
+            Confidence::Medium => write!(f, "medium"),
+            Confidence::High => write!(f, "high"),
+        }
+    }
+}
+
 /// How a finding can be automatically fixed
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum FixKind {
@@ -32,6 +71,118 @@ pub enum FixKind {
     ReplacePattern { find: String, replace: String },
     /// Suggestion only — no auto-fix available
     Suggestion,
+    /// Remove a `revet-ignore` comment on the offending line, or only
+    /// `token` from its rule list (`SUPP` findings)
+    RemoveSuppression { token: Option<String> },
+    /// Delete `statement` from the offending line, and the line itself when
+    /// nothing else is left on it (`DBG` debugger statements)
+    RemoveStatement { statement: String },
+    /// Re-run a code generator and verify its output (`GEN` findings)
+    Regenerate {
+        command: String,
+        repo_root: PathBuf,
+        spec: String,
+        output_dir: String,
+        hash_file: String,
+    },
+}
+
+/// Code a suggestion proposes, kept apart from its prose
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuggestedCode {
+    /// Code-fence language (`python`, `tsx`, ...)
+    pub language: String,
+    pub snippet: String,
+    /// Where the snippet goes in the finding's file
+    pub insert_context: InsertContext,
+}
+
+/// Where a [`SuggestedCode`] snippet goes, relative to the finding's line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InsertContext {
+    /// Replaces the line, keeping its indentation
+    Line,
+    /// Replaces the first occurrence of `original` from the start of the
+    /// line on, which may run over several lines
+    Replace { original: String },
+}
+
+/// Soft cap, in characters, on [`Finding::message`]. Analyzers keep the
+/// message a one-line headline and put anything longer in
+/// [`Finding::details`]; a test over the fixtures holds them to it.
+pub const HEADLINE_MAX: usize = 200;
+
+/// Long-form content behind a finding's headline.
+///
+/// Outputs render it as they can: the terminal under `--verbose`, JSON and
+/// SARIF in full, markdown in a collapsible section, GitHub annotations not
+/// at all (they point to `revet explain <id>` instead).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FindingDetails {
+    /// Query the finding is about, as written in the source
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_text: Option<String>,
+
+    /// Every location involved ("path:line")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub location_list: Vec<String>,
+
+    /// Versions in use and where, most used first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub version_table: Vec<VersionRow>,
+
+    /// Anything else worth reading past the headline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub free_text: Option<String>,
+}
+
+/// One row of [`FindingDetails::version_table`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct VersionRow {
+    pub version: String,
+    /// Where the version is used (a package, or "path:line")
+    pub used_by: Vec<String>,
+}
+
+impl FindingDetails {
+    pub fn is_empty(&self) -> bool {
+        self.query_text.is_none()
+            && self.location_list.is_empty()
+            && self.version_table.is_empty()
+            && self.free_text.is_none()
+    }
+
+    /// Plain-text lines for outputs without structure of their own
+    /// (terminal, `revet explain`, markdown)
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(text) = &self.free_text {
+            lines.extend(text.lines().map(String::from));
+        }
+        if let Some(query) = &self.query_text {
+            lines.push("Query:".to_string());
+            lines.extend(query.lines().map(|l| format!("    {}", l)));
+        }
+        if !self.version_table.is_empty() {
+            lines.push("Versions:".to_string());
+            for row in &self.version_table {
+                lines.push(format!(
+                    "    {} ({}): {}",
+                    row.version,
+                    row.used_by.len(),
+                    row.used_by.join(", ")
+                ));
+            }
+        }
+        if !self.location_list.is_empty() {
+            lines.push(format!("Locations ({}):", self.location_list.len()));
+            lines.extend(self.location_list.iter().map(|l| format!("    {}", l)));
+        }
+        lines
+    }
 }
 
 /// A single finding from analysis
@@ -43,15 +194,33 @@ pub struct Finding {
     /// Severity level
     pub severity: Severity,
 
-    /// Human-readable message
+    /// Short human-readable headline, at most [`HEADLINE_MAX`] characters
     pub message: String,
 
-    /// File where the finding was detected
+    /// Long-form content behind `message` (full query text, every location,
+    /// version tables)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<FindingDetails>,
+
+    /// File where the finding was detected; empty for a finding about the
+    /// repository as a whole (see [`Finding::location`])
     pub file: PathBuf,
 
-    /// Line number in the file
+    /// Line number in the file; 0 for repository-level findings
     pub line: usize,
 
+    /// 1-based column (in characters) where the offending code starts on `line`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+
+    /// 1-based column one past the end of the offending code (same line)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+
+    /// How likely this finding is a true positive
+    #[serde(default, skip_serializing_if = "Confidence::is_high")]
+    pub confidence: Confidence,
+
     /// Number of downstream dependents affected
     pub affected_dependents: usize,
 
@@ -59,6 +228,11 @@ pub struct Finding {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub suggestion: Option<String>,
 
+    /// Code the suggestion proposes, rendered as a code block after it.
+    /// Reviews drop it when it does not parse (see [`crate::suggestion`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion_code: Option<SuggestedCode>,
+
     /// How this finding can be auto-fixed (None = no fix metadata)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub fix_kind: Option<FixKind>,
@@ -71,9 +245,58 @@ pub struct Finding {
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub ai_false_positive: bool,
 
+    /// Why an eligible finding was not sent for AI enrichment (`"budget"`
+    /// when higher-priority findings used up `max_cost_per_run`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_skipped: Option<String>,
+
     /// Caller locations ("path:line") affected by this finding (e.g. impact analysis)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub callers: Vec<String>,
+
+    /// Byte spans `(start, end)` of matched secret values on `line`.
+    /// Output channels mask these spans when printing source text.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secret_spans: Vec<(usize, usize)>,
+
+    /// Redacted form of `message`, set only when `message` itself carries a
+    /// raw secret (redaction disabled). Used for baselines and run logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redacted_message: Option<String>,
+
+    /// Sub-rule that produced this finding, qualified by module once the
+    /// dispatcher has seen it (e.g. `"error_handling:swallowed"`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+
+    /// In a file with a generated-code marker. Set by reviews under
+    /// `[files] generated = "info-only"` (the finding is then downgraded to
+    /// info and not counted by the fail-on gate) and by `revet baseline`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub generated: bool,
+
+    /// Whether the file is production, test or script code. Set by reviews
+    /// (see [`code_kind`](crate::code_kind)); omitted for production code.
+    #[serde(default, skip_serializing_if = "CodeKind::is_production")]
+    pub code_kind: CodeKind,
+
+    /// External tool that reported an imported finding (`semgrep`,
+    /// `gitleaks`); `None` for revet's own (see [`crate::import`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_tool: Option<String>,
+
+    /// The external tool's short description of `rule`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_description: Option<String>,
+
+    /// Catalog entry `message` was rendered from. Outputs render it again in
+    /// the selected locale; `None` for messages not yet in the catalog.
+    #[serde(skip)]
+    pub message_ref: Option<Msg>,
+
+    /// Catalog entry `suggestion` was rendered from
+    #[serde(skip)]
+    pub suggestion_ref: Option<Msg>,
 }
 
 impl Default for Finding {
@@ -82,14 +305,87 @@ impl Default for Finding {
             id: String::new(),
             severity: Severity::Info,
             message: String::new(),
+            details: None,
             file: PathBuf::new(),
             line: 0,
+            column: None,
+            end_column: None,
+            confidence: Confidence::High,
             affected_dependents: 0,
             suggestion: None,
+            suggestion_code: None,
             fix_kind: None,
             ai_note: None,
             ai_false_positive: false,
+            ai_skipped: None,
             callers: Vec::new(),
+            secret_spans: Vec::new(),
+            redacted_message: None,
+            rule: None,
+            generated: false,
+            code_kind: CodeKind::Production,
+            source_tool: None,
+            rule_description: None,
+            message_ref: None,
+            suggestion_ref: None,
+        }
+    }
+}
+
+/// Where a finding points: a position in a file, or the repository itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FindingLocation<'a> {
+    File {
+        path: &'a Path,
+        line: usize,
+        column: Option<usize>,
+    },
+    /// A property of the repository (a missing LICENSE, the configuration),
+    /// not of any line
+    Repository,
+}
+
+impl Finding {
+    /// Where this finding points. Repository-level findings keep the JSON
+    /// shape of every other finding, with an empty `file` and `line` 0.
+    pub fn location(&self) -> FindingLocation<'_> {
+        if self.is_repository_level() {
+            FindingLocation::Repository
+        } else {
+            FindingLocation::File {
+                path: &self.file,
+                line: self.line,
+                column: self.column,
+            }
+        }
+    }
+
+    /// Whether this finding is about the repository rather than a file
+    pub fn is_repository_level(&self) -> bool {
+        self.file.as_os_str().is_empty()
+    }
+
+    /// Message with any secret values masked, regardless of redaction settings.
+    ///
+    /// Baselines and persisted logs key on this so toggling `redact_secrets`
+    /// doesn't resurface baselined findings.
+    pub fn redacted_message(&self) -> &str {
+        self.redacted_message.as_deref().unwrap_or(&self.message)
+    }
+
+    /// Message in `locale`, or the English `message` when it has no catalog entry
+    pub fn localized_message(&self, locale: Locale) -> String {
+        match &self.message_ref {
+            Some(msg) => msg.render(locale),
+            None => self.message.clone(),
+        }
+    }
+
+    /// Suggestion in `locale`, or the English `suggestion` when it has no catalog entry
+    pub fn localized_suggestion(&self, locale: Locale) -> Option<String> {
+        match &self.suggestion_ref {
+            Some(msg) => Some(msg.render(locale)),
+            None => self.suggestion.clone(),
         }
     }
 }
@@ -105,6 +401,37 @@ pub struct ReviewSummary {
     /// Count of analyzed files per language (e.g., "Rust" → 38, "Python" → 4)
     #[serde(default)]
     pub files_by_language: HashMap<String, usize>,
+    /// Findings in generated files (`[files] generated = "info-only"`),
+    /// counted here instead of in `info` so the fail-on gate ignores them
+    #[serde(default)]
+    pub generated: usize,
+    /// Complexity findings reported and deferred, when the complexity
+    /// analyzer ran in delta mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complexity_delta: Option<DeltaSummary>,
+    /// Documentation coverage of the public surface, when `DOC` ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_coverage: Option<DocCoverage>,
+    /// Phases skipped to stay within `--time-budget`, when one was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<BudgetReport>,
+    /// Load shed to stay under `[performance] max_memory_mb`, when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemoryReport>,
+    /// Analyzer timings, with `--timings`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<TimingReport>,
+    /// Findings of modules in shadow mode (`rollout_percent`), counted here
+    /// instead of above so neither output nor the fail-on gate sees them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shadow: Vec<ShadowModule>,
+    /// Findings hidden by `revet ignore` entries; `Some` whenever entries
+    /// applied to the run, even if they hid nothing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_ignored: Option<usize>,
+    /// Features that skipped objects a partial clone lacks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub degraded: Vec<Degradation>,
 }
 
 impl ReviewSummary {
@@ -1,9 +1,16 @@
 //! Auto-fix engine — applies safe, deterministic fixes to source files
 //!
 //! Groups fixable findings by file, applies line-level transformations,
-//! and returns a report. Files are modified in-place (user reviews via `git diff`).
+//! and returns a report. Files are modified in-place (user reviews via `git diff`),
+//! through the [`MutationJournal`] that records them in the audit log.
+//!
+//! The report keeps a [`LineMap`] per edited file, so line numbers taken
+//! before the fixes — the other findings, the baseline's anchors — can be
+//! moved to where their text is now (see [`FixReport::reconcile`]).
 
+use crate::audit::MutationJournal;
 use crate::finding::{Finding, FixKind};
+use crate::line_map::LineMap;
 use anyhow::{Context, Result};
 use regex::Regex;
 use std::collections::HashMap;
@@ -20,16 +27,78 @@ pub struct FixResult {
 /// Summary of all fixes applied
 #[derive(Debug, Clone, Default)]
 pub struct FixReport {
-    /// Number of fixes actually applied (CommentOut + ReplacePattern)
+    /// Number of fixes actually applied (CommentOut, ReplacePattern, Regenerate)
     pub applied: usize,
     /// Number of suggestion-only findings (not auto-fixable)
     pub skipped: usize,
     /// Details of each applied fix
     pub results: Vec<FixResult>,
+    /// Line mapping of each file the fixes edited
+    pub line_maps: HashMap<PathBuf, LineMap>,
+    /// Findings and baseline entries dropped by [`reconcile`](Self::reconcile)
+    /// and [`Baseline::reconcile`](crate::Baseline::reconcile) because a fix
+    /// deleted their line
+    pub dropped: Vec<DroppedAnchor>,
+}
+
+/// What pointed at a line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Anchor {
+    /// A finding, by ID
+    Finding(String),
+    /// A baseline entry, by message
+    BaselineEntry(String),
+}
+
+/// Something that pointed at a line a fix deleted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DroppedAnchor {
+    pub file: PathBuf,
+    /// The line before the fixes
+    pub line: usize,
+    pub anchor: Anchor,
+}
+
+impl FixReport {
+    /// Where `line` of `file` is after the fixes; `None` when a fix deleted
+    /// it, which is logged in [`dropped`](Self::dropped) for `anchor`
+    pub fn remap(
+        &mut self,
+        file: &Path,
+        line: usize,
+        anchor: impl FnOnce() -> Anchor,
+    ) -> Option<usize> {
+        let Some(map) = self.line_maps.get(file) else {
+            return Some(line);
+        };
+        let mapped = map.map(line);
+        if mapped.is_none() {
+            self.dropped.push(DroppedAnchor {
+                file: file.to_path_buf(),
+                line,
+                anchor: anchor(),
+            });
+        }
+        mapped
+    }
+
+    /// Move `findings` taken before the fixes to their lines after them,
+    /// dropping those whose line a fix deleted
+    pub fn reconcile(&mut self, findings: Vec<Finding>) -> Vec<Finding> {
+        findings
+            .into_iter()
+            .filter_map(|mut finding| {
+                let id = &finding.id;
+                finding.line =
+                    self.remap(&finding.file, finding.line, || Anchor::Finding(id.clone()))?;
+                Some(finding)
+            })
+            .collect()
+    }
 }
 
 /// Determine the comment prefix for a file based on its extension
-fn comment_prefix(path: &Path) -> &'static str {
+pub(crate) fn comment_prefix(path: &Path) -> &'static str {
     let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
     match ext {
         "py" | "tf" | "tfvars" | "yaml" | "yml" | "toml" | "sh" | "bash" | "rb" | "r" => "#",
@@ -39,25 +108,154 @@ fn comment_prefix(path: &Path) -> &'static str {
     }
 }
 
+/// A concrete edit that fixes one finding: replace the text of `line`
+/// between `start_column` and `end_column` with `new_text`
+///
+/// Columns are 1-based character columns, like [`Finding::column`]; an
+/// insertion has `start_column == end_column`. `new_text` may span lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixPlan {
+    pub finding_id: String,
+    pub line: usize,
+    pub start_column: usize,
+    pub end_column: usize,
+    pub new_text: String,
+    /// The edit empties the whole line, which is deleted with its line break
+    pub removes_line: bool,
+}
+
+impl FixPlan {
+    /// Whether this edit and `other` touch the same text (or insert at the
+    /// same point), so applying both is ambiguous
+    pub fn overlaps(&self, other: &FixPlan) -> bool {
+        self.line == other.line
+            && if self.removes_line || other.removes_line {
+                true
+            } else if self.start_column == self.end_column && other.start_column == other.end_column
+            {
+                self.start_column == other.start_column
+            } else {
+                self.start_column < other.end_column && other.start_column < self.end_column
+            }
+    }
+}
+
+/// Plan the edit that fixes `finding`, given the current `content` of its file
+///
+/// Returns `None` for suggestion-only findings, generator fixes, and
+/// patterns that no longer change the line.
+pub fn plan_fix(finding: &Finding, content: &str) -> Option<FixPlan> {
+    let original = content.lines().nth(finding.line.checked_sub(1)?)?;
+    let mut removes_line = false;
+    let (start_column, end_column, new_text) = match finding.fix_kind.as_ref()? {
+        FixKind::CommentOut => {
+            let prefix = comment_prefix(&finding.file);
+            let suggestion = finding.suggestion.as_deref().unwrap_or("Review this line");
+            (
+                1,
+                1,
+                format!("{} FIXME(revet): {}\n{} ", prefix, suggestion, prefix),
+            )
+        }
+        FixKind::ReplacePattern { find, replace } => {
+            let caps = Regex::new(find).ok()?.captures(original)?;
+            let matched = caps.get(0)?;
+            let mut new_text = String::new();
+            caps.expand(replace, &mut new_text);
+            if new_text == matched.as_str() {
+                return None;
+            }
+            (
+                original[..matched.start()].chars().count() + 1,
+                original[..matched.end()].chars().count() + 1,
+                new_text,
+            )
+        }
+        FixKind::RemoveSuppression { token } => {
+            let (start, end, whole_line) =
+                crate::suppress::removal_range(original, token.as_deref())?;
+            removes_line = whole_line;
+            (start, end, String::new())
+        }
+        FixKind::RemoveStatement { statement } => {
+            let start = original.find(statement.as_str())?;
+            let end = start + statement.len();
+            let rest = format!("{}{}", &original[..start], &original[end..]);
+            if rest.trim().trim_matches(';').trim().is_empty() {
+                removes_line = true;
+                (1, original.chars().count() + 1, String::new())
+            } else {
+                (
+                    original[..start].chars().count() + 1,
+                    original[..end].chars().count() + 1,
+                    String::new(),
+                )
+            }
+        }
+        FixKind::Suggestion | FixKind::Regenerate { .. } => return None,
+    };
+    Some(FixPlan {
+        finding_id: finding.id.clone(),
+        line: finding.line,
+        start_column,
+        end_column,
+        new_text,
+        removes_line,
+    })
+}
+
+/// Plan the fixes for `findings` in one file whose text is `content`
+///
+/// The plans are sorted by position and never overlap: when two fixes touch
+/// the same text, the one for the earlier finding wins.
+pub fn plan_fixes<'a>(
+    findings: impl IntoIterator<Item = &'a Finding>,
+    content: &str,
+) -> Vec<FixPlan> {
+    let mut plans: Vec<FixPlan> = Vec::new();
+    for plan in findings.into_iter().filter_map(|f| plan_fix(f, content)) {
+        if !plans.iter().any(|p| p.overlaps(&plan)) {
+            plans.push(plan);
+        }
+    }
+    plans.sort_by_key(|p| (p.line, p.start_column, p.end_column));
+    plans
+}
+
+/// Byte offset of 1-based character `column` in `line` (clamped to its end)
+fn byte_offset(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .nth(column.saturating_sub(1))
+        .map_or(line.len(), |(i, _)| i)
+}
+
 /// Apply auto-fixes for all fixable findings.
 ///
-/// Fixes are grouped by file and applied in reverse line order so that line
-/// numbers remain valid. Only `CommentOut` and `ReplacePattern` are applied;
-/// `Suggestion`-only findings are counted but skipped.
-pub fn apply_fixes(findings: &[Finding]) -> Result<FixReport> {
+/// Fixes are grouped by file, planned against the file's current text (see
+/// [`plan_fixes`]) and applied from the end of the file backwards so that
+/// positions remain valid. `CommentOut`, `ReplacePattern`,
+/// `RemoveSuppression` and `RemoveStatement` are applied,
+/// then any `Regenerate` commands; `Suggestion`-only findings are counted
+/// but skipped. Every change is recorded in `journal`.
+pub fn apply_fixes(findings: &[Finding], journal: &mut MutationJournal) -> Result<FixReport> {
     let mut report = FixReport::default();
 
     // Group findings by file, only including those with actionable fix_kind
     let mut by_file: HashMap<PathBuf, Vec<&Finding>> = HashMap::new();
+    let mut regenerations: Vec<&Finding> = Vec::new();
 
     for finding in findings {
         match &finding.fix_kind {
-            Some(FixKind::CommentOut) | Some(FixKind::ReplacePattern { .. }) => {
+            Some(FixKind::CommentOut)
+            | Some(FixKind::ReplacePattern { .. })
+            | Some(FixKind::RemoveSuppression { .. })
+            | Some(FixKind::RemoveStatement { .. }) => {
                 by_file
                     .entry(finding.file.clone())
                     .or_default()
                     .push(finding);
             }
+            Some(FixKind::Regenerate { .. }) => regenerations.push(finding),
             Some(FixKind::Suggestion) => {
                 report.skipped += 1;
             }
@@ -67,7 +265,7 @@ pub fn apply_fixes(findings: &[Finding]) -> Result<FixReport> {
         }
     }
 
-    for (file_path, mut file_findings) in by_file {
+    for (file_path, file_findings) in by_file {
         if file_path.as_os_str().is_empty() || !file_path.exists() {
             continue;
         }
@@ -76,50 +274,26 @@ pub fn apply_fixes(findings: &[Finding]) -> Result<FixReport> {
             .with_context(|| format!("Failed to read {}", file_path.display()))?;
 
         let mut lines: Vec<String> = content.lines().map(String::from).collect();
+        let mut fixed: Vec<String> = Vec::new();
 
-        // Sort by line number descending so edits don't shift subsequent lines
-        file_findings.sort_by(|a, b| b.line.cmp(&a.line));
-
-        for finding in &file_findings {
-            let line_idx = finding.line.saturating_sub(1);
-            if line_idx >= lines.len() {
-                continue;
-            }
-
-            match &finding.fix_kind {
-                Some(FixKind::CommentOut) => {
-                    let prefix = comment_prefix(&file_path);
-                    let suggestion = finding.suggestion.as_deref().unwrap_or("Review this line");
-                    let original = &lines[line_idx];
-                    let commented = format!(
-                        "{} FIXME(revet): {}\n{} {}",
-                        prefix, suggestion, prefix, original
-                    );
-                    lines[line_idx] = commented;
-                    report.applied += 1;
-                    report.results.push(FixResult {
-                        file: file_path.clone(),
-                        line: finding.line,
-                        finding_id: finding.id.clone(),
-                    });
-                }
-                Some(FixKind::ReplacePattern { find, replace }) => {
-                    if let Ok(re) = Regex::new(find) {
-                        let original = &lines[line_idx];
-                        let fixed = re.replace(original, replace.as_str()).to_string();
-                        if fixed != *original {
-                            lines[line_idx] = fixed;
-                            report.applied += 1;
-                            report.results.push(FixResult {
-                                file: file_path.clone(),
-                                line: finding.line,
-                                finding_id: finding.id.clone(),
-                            });
-                        }
-                    }
-                }
-                _ => {}
+        // Apply last edit first so earlier positions stay valid
+        let plans = plan_fixes(file_findings, &content);
+        for plan in plans.iter().rev() {
+            if plan.removes_line {
+                lines.remove(plan.line - 1);
+            } else {
+                let line = &mut lines[plan.line - 1];
+                let start = byte_offset(line, plan.start_column);
+                let end = byte_offset(line, plan.end_column);
+                line.replace_range(start..end, &plan.new_text);
             }
+            report.applied += 1;
+            fixed.insert(0, plan.finding_id.clone());
+            report.results.push(FixResult {
+                file: file_path.clone(),
+                line: plan.line,
+                finding_id: plan.finding_id.clone(),
+            });
         }
 
         // Write back
@@ -131,8 +305,43 @@ pub fn apply_fixes(findings: &[Finding]) -> Result<FixReport> {
             output
         };
 
-        std::fs::write(&file_path, &output)
-            .with_context(|| format!("Failed to write {}", file_path.display()))?;
+        journal.write(&file_path, &output, &fixed)?;
+        report
+            .line_maps
+            .insert(file_path, LineMap::from_plans(&plans));
+    }
+
+    // Generator commands run after line edits, once each
+    let mut seen = std::collections::HashSet::new();
+    for finding in regenerations {
+        if let Some(FixKind::Regenerate {
+            command,
+            repo_root,
+            spec,
+            output_dir,
+            hash_file,
+        }) = &finding.fix_kind
+        {
+            if !seen.insert((repo_root, output_dir)) {
+                continue;
+            }
+            crate::analyzer::generated::regenerate(
+                command,
+                repo_root,
+                spec,
+                output_dir,
+                hash_file,
+                &finding.id,
+                journal,
+            )
+            .with_context(|| format!("Failed to regenerate {}", output_dir))?;
+            report.applied += 1;
+            report.results.push(FixResult {
+                file: finding.file.clone(),
+                line: finding.line,
+                finding_id: finding.id.clone(),
+            });
+        }
     }
 
     Ok(report)
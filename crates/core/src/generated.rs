@@ -0,0 +1,209 @@
+//! Generated-file detection.
+//!
+//! A file is generated when one of its first `[files] marker_lines` lines
+//! matches a `[files] generated_markers` regex — Go's `// Code generated
+//! ... DO NOT EDIT.`, the `@generated` convention, `<auto-generated>`
+//! headers and the like. Markers follow the file wherever a generator puts
+//! it, so there are no exclude globs to keep in sync with output layouts.
+//!
+//! `[files] generated` decides what review does with these files:
+//!
+//! - `skip` — no findings are reported in them. They are still parsed, so
+//!   symbols other files import from them resolve in the graph.
+//! - `info-only` — their findings are downgraded to info, flagged
+//!   [`Finding::generated`] and not counted by the fail-on gate.
+//! - `analyze` — they are treated like any other file.
+//!
+//! Whatever the policy, the classification is recorded in the coverage
+//! ledger and in baselines.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+use regex::Regex;
+
+use crate::config::FilesConfig;
+use crate::{CoverageLedger, Finding, Severity, SkipReason};
+
+/// Markers checked when `[files] generated_markers` is not set
+pub const DEFAULT_MARKERS: &[&str] = &[
+    // Go's official convention (https://go.dev/s/generatedcode)
+    r"^// Code generated .* DO NOT EDIT\.$",
+    // Phabricator / Meta convention, used by many JS and Python generators
+    r"@generated\b",
+    // .NET tools and T4 templates
+    r"<auto-generated",
+    // protoc, flatc, thrift, ...: "Generated by X. DO NOT EDIT!"
+    r"(?i)\bgenerated by\b.*\bdo not edit\b",
+    // "This file was automatically generated", "Autogenerated file"
+    r"(?i)^\W*(this (file|code) (is|was|has been) )?auto(matically)?[- ]?generated\b",
+];
+
+/// What review does with generated files (`[files] generated`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratedPolicy {
+    Skip,
+    InfoOnly,
+    Analyze,
+}
+
+impl GeneratedPolicy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "skip" => Some(Self::Skip),
+            "info-only" => Some(Self::InfoOnly),
+            "analyze" => Some(Self::Analyze),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Skip => "skip",
+            Self::InfoOnly => "info-only",
+            Self::Analyze => "analyze",
+        }
+    }
+}
+
+/// Marker-based classifier built from `[files]`
+#[derive(Debug, Clone)]
+pub struct GeneratedFiles {
+    markers: Vec<Regex>,
+    lines: usize,
+    policy: GeneratedPolicy,
+}
+
+impl GeneratedFiles {
+    /// Invalid markers and an unknown policy are reported by
+    /// [`RevetConfig::validate`](crate::RevetConfig::validate); here they
+    /// are ignored and fall back to `skip`.
+    pub fn from_config(config: &FilesConfig) -> Self {
+        Self {
+            markers: config
+                .generated_markers
+                .iter()
+                .filter_map(|m| Regex::new(m).ok())
+                .collect(),
+            lines: config.marker_lines,
+            policy: GeneratedPolicy::parse(&config.generated).unwrap_or(GeneratedPolicy::Skip),
+        }
+    }
+
+    pub fn policy(&self) -> GeneratedPolicy {
+        self.policy
+    }
+
+    /// Whether one of the first lines of `path` carries a marker. Reading
+    /// stops at the first line that is not UTF-8 text.
+    pub fn is_generated(&self, path: &Path) -> bool {
+        if self.markers.is_empty() {
+            return false;
+        }
+        let Ok(file) = File::open(path) else {
+            return false;
+        };
+        BufReader::new(file)
+            .lines()
+            .take(self.lines)
+            .map_while(|line| line.ok())
+            .any(|line| self.is_marker(&line))
+    }
+
+    /// Whether `line` matches a marker
+    pub fn is_marker(&self, line: &str) -> bool {
+        let line = line.trim_end_matches('\r');
+        self.markers.iter().any(|m| m.is_match(line))
+    }
+
+    /// Classify every file in `files`
+    pub fn classify(&self, files: &[PathBuf]) -> GeneratedSet {
+        GeneratedSet {
+            files: files
+                .par_iter()
+                .filter(|f| self.is_generated(f))
+                .cloned()
+                .collect(),
+            policy: self.policy,
+        }
+    }
+}
+
+/// The generated files of one run and the policy that applies to them
+#[derive(Debug, Clone)]
+pub struct GeneratedSet {
+    files: HashSet<PathBuf>,
+    policy: GeneratedPolicy,
+}
+
+impl GeneratedSet {
+    pub fn policy(&self) -> GeneratedPolicy {
+        self.policy
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Whether `path` (as discovered, or relative to `repo_root`) is generated
+    pub fn contains(&self, path: &Path, repo_root: &Path) -> bool {
+        self.files.contains(path)
+            || (path.is_relative() && self.files.contains(&repo_root.join(path)))
+    }
+
+    /// Files content analyzers should scan: all of `files`, minus the
+    /// generated ones under `skip`
+    pub fn analysis_files(&self, files: &[PathBuf]) -> Vec<PathBuf> {
+        match self.policy {
+            GeneratedPolicy::Skip => files
+                .iter()
+                .filter(|f| !self.files.contains(*f))
+                .cloned()
+                .collect(),
+            _ => files.to_vec(),
+        }
+    }
+
+    /// Record the classification; under `skip`, generated files are
+    /// recorded as skipped even though they were parsed
+    pub fn record(&self, ledger: &mut CoverageLedger) {
+        for path in &self.files {
+            ledger.generated(path);
+            if self.policy == GeneratedPolicy::Skip {
+                ledger.dropped(path, SkipReason::Generated);
+            }
+        }
+    }
+
+    /// Apply the policy to findings from any analyzer: drop the ones in
+    /// generated files under `skip`, downgrade and flag them under
+    /// `info-only`
+    pub fn apply(&self, findings: Vec<Finding>, repo_root: &Path) -> Vec<Finding> {
+        if self.files.is_empty() || self.policy == GeneratedPolicy::Analyze {
+            return findings;
+        }
+        findings
+            .into_iter()
+            .filter_map(|mut f| {
+                if !self.contains(&f.file, repo_root) {
+                    return Some(f);
+                }
+                match self.policy {
+                    GeneratedPolicy::Skip => None,
+                    _ => {
+                        f.severity = Severity::Info;
+                        f.generated = true;
+                        Some(f)
+                    }
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,588 @@
+//! Partial clones and sparse checkouts
+//!
+//! A blobless clone (`git clone --filter=blob:none`) has every commit and
+//! tree but only the file contents it checked out; the rest stay on the
+//! promisor remote. libgit2 never fetches them, so a blob of another commit
+//! reads as "object not found" — which in a full clone would mean the
+//! repository is corrupt — and `git blame` silently credits every line to
+//! the newest commit whose contents it has. [`GitObjects`] is how revet
+//! reads objects other than the working tree's: it tells the two cases
+//! apart, fetches missing blobs in batches when `[git] allow_fetch`
+//! permits, and otherwise lets the feature skip them, counting each skip
+//! as a [`Degradation`] the summary and the coverage report show.
+//!
+//! A sparse checkout leaves the files outside its cone out of the working
+//! tree. [`SparseCheckout`] says which paths those are, so discovery can
+//! leave them out too.
+
+use anyhow::{anyhow, Result};
+use git2::{ErrorClass, ErrorCode, Oid, Repository};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+use crate::config::GitConfig;
+use crate::coverage::{CoverageLedger, SkipReason};
+
+/// A feature that runs on less than the full history in a partial clone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    /// Changed lines of a diff; a file whose base version is missing
+    /// counts as wholly changed
+    ChangedLines,
+    /// Blame, for ownership and suggested reviewers
+    Attribution,
+    /// The `HIST` scan of the last commits for removed secrets
+    HistorySecrets,
+    /// Code graph of the diff base, for impact analysis
+    RefBaseline,
+    /// Single files read at another ref (complexity delta, config drift)
+    RefFiles,
+    /// Rename detection in hotspot churn
+    RenameTracking,
+}
+
+impl Feature {
+    /// What a skip costs the feature, for `count` skips
+    fn skipped(self, count: usize) -> String {
+        let what = match self {
+            Self::ChangedLines => "file(s) treated as wholly changed",
+            Self::Attribution => "file(s) not blamed",
+            Self::HistorySecrets => "file change(s) not scanned",
+            Self::RefBaseline => "file(s) left out",
+            Self::RefFiles => "file(s) not read",
+            Self::RenameTracking => return "exact renames only".to_string(),
+        };
+        format!("{} {}", count, what)
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::ChangedLines => "changed lines",
+            Self::Attribution => "attribution",
+            Self::HistorySecrets => "history secret scan",
+            Self::RefBaseline => "ref baseline",
+            Self::RefFiles => "files at the base ref",
+            Self::RenameTracking => "rename tracking",
+        })
+    }
+}
+
+/// A feature that skipped objects the partial clone does not have
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Degradation {
+    pub feature: Feature,
+    /// Files (or file changes) skipped; 0 for [`Feature::RenameTracking`]
+    pub skipped: usize,
+    /// Why they were not fetched
+    pub reason: String,
+}
+
+impl fmt::Display for Degradation {
+    /// `attribution: 3 file(s) not blamed — not in the partial clone, and
+    /// `[git] allow_fetch = false``
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} \u{2014} {}",
+            self.feature,
+            self.feature.skipped(self.skipped),
+            self.reason
+        )
+    }
+}
+
+/// A file at a ref whose contents the partial clone does not have and
+/// could not fetch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingBlob {
+    pub path: PathBuf,
+    pub ref_spec: String,
+}
+
+impl fmt::Display for MissingBlob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at `{}` is not in this partial clone",
+            self.path.display(),
+            self.ref_spec
+        )
+    }
+}
+
+impl std::error::Error for MissingBlob {}
+
+/// How the repository was cloned and checked out
+#[derive(Debug, Clone, Default)]
+pub struct CloneLayout {
+    /// Remote a partial clone gets missing objects from
+    pub promisor: Option<String>,
+    /// Its object filter (`blob:none`, `tree:0`, ...)
+    pub filter: Option<String>,
+    /// Set in a sparse checkout
+    pub sparse: Option<SparseCheckout>,
+}
+
+impl CloneLayout {
+    /// Read the layout from the repository's config, index and
+    /// `info/sparse-checkout`
+    pub fn detect(repo: &Repository) -> Self {
+        let mut layout = Self::default();
+        let Ok(config) = repo.config() else {
+            return layout;
+        };
+
+        layout.promisor = promisor_remote(&config);
+        if let Some(remote) = &layout.promisor {
+            layout.filter = config
+                .get_string(&format!("remote.{}.partialclonefilter", remote))
+                .ok();
+        }
+
+        let sparse_config = config.get_bool("core.sparsecheckout").unwrap_or(false);
+        let checkout = SparseCheckout::read(repo);
+        if sparse_config || !checkout.skipped.is_empty() {
+            layout.sparse = Some(checkout);
+        }
+        layout
+    }
+
+    pub fn is_partial(&self) -> bool {
+        self.promisor.is_some()
+    }
+}
+
+/// The remote a partial clone gets missing objects from:
+/// `remote.<name>.promisor = true`, or the older
+/// `extensions.partialClone = <name>`
+fn promisor_remote(config: &git2::Config) -> Option<String> {
+    if let Ok(mut entries) = config.entries(Some(r"remote\..*\.promisor")) {
+        while let Some(Ok(entry)) = entries.next() {
+            let promisor = entry.value().is_some_and(|v| v == "true");
+            let remote = entry
+                .name()
+                .and_then(|n| n.strip_prefix("remote."))
+                .and_then(|n| n.strip_suffix(".promisor"));
+            if let (true, Some(remote)) = (promisor, remote) {
+                return Some(remote.to_string());
+            }
+        }
+    }
+    config.get_string("extensions.partialclone").ok()
+}
+
+/// Whether `repo` is a partial clone, from its config alone
+pub fn is_partial_clone(repo: &Repository) -> bool {
+    repo.config()
+        .ok()
+        .and_then(|config| promisor_remote(&config))
+        .is_some()
+}
+
+/// Which paths a sparse checkout leaves out of the working tree
+#[derive(Debug, Clone, Default)]
+pub struct SparseCheckout {
+    /// Cone-mode directories checked out with everything below them
+    recursive: HashSet<String>,
+    /// Cone-mode directories whose files, but not subdirectories, are
+    /// checked out
+    parents: HashSet<String>,
+    /// The patterns are cone-shaped
+    cone: bool,
+    /// Tracked paths (and sparse-index directories, with a trailing `/`)
+    /// marked skip-worktree
+    skipped: HashSet<String>,
+    /// Every tracked path, so untracked ones fall back to the cone
+    tracked: HashSet<String>,
+}
+
+impl SparseCheckout {
+    fn read(repo: &Repository) -> Self {
+        let mut checkout = Self::default();
+        if let Ok(index) = repo.index() {
+            for entry in index.iter() {
+                let path = String::from_utf8_lossy(&entry.path).into_owned();
+                let flags = git2::IndexEntryExtendedFlag::from_bits_truncate(entry.flags_extended);
+                if flags.contains(git2::IndexEntryExtendedFlag::SKIP_WORKTREE) {
+                    checkout.skipped.insert(path.clone());
+                }
+                checkout.tracked.insert(path);
+            }
+        }
+        if let Ok(patterns) = std::fs::read_to_string(repo.path().join("info/sparse-checkout")) {
+            checkout.parse_cone(&patterns);
+        }
+        checkout
+    }
+
+    /// Read cone-mode patterns: `/*` and `!/*/`, then `/dir/` for each
+    /// directory in the cone, followed by `!/dir/*/` when only its files
+    /// are
+    fn parse_cone(&mut self, patterns: &str) {
+        for line in patterns.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line == "/*" || line == "!/*/" {
+                continue;
+            }
+            if let Some(dir) = line.strip_prefix("!/").and_then(|l| l.strip_suffix("/*/")) {
+                self.recursive.remove(dir);
+                self.parents.insert(dir.to_string());
+            } else if let Some(dir) = line.strip_prefix('/').and_then(|l| l.strip_suffix('/')) {
+                self.recursive.insert(dir.to_string());
+            } else {
+                // Not cone-shaped: the index alone decides
+                self.recursive.clear();
+                self.parents.clear();
+                return;
+            }
+        }
+        self.cone = true;
+    }
+
+    /// Whether `rel` (relative to the work tree, `/`-separated) is in the
+    /// checkout. Tracked paths follow their skip-worktree bit; untracked
+    /// ones the cone.
+    pub fn contains(&self, rel: &str) -> bool {
+        if self.skipped.contains(rel) {
+            return false;
+        }
+        let ancestors: Vec<&str> = rel.match_indices('/').map(|(i, _)| &rel[..i]).collect();
+        if ancestors
+            .iter()
+            .any(|dir| self.skipped.contains(&format!("{}/", dir)))
+        {
+            return false;
+        }
+        if self.tracked.contains(rel) || !self.cone {
+            return true;
+        }
+        match ancestors.last() {
+            None => true,
+            Some(parent) => {
+                self.parents.contains(*parent)
+                    || ancestors.iter().any(|dir| self.recursive.contains(*dir))
+            }
+        }
+    }
+}
+
+/// Why a missing object was not fetched
+#[derive(Debug, Clone, Default)]
+enum FetchState {
+    #[default]
+    Ready,
+    /// A fetch would go past `[git] fetch_limit`
+    LimitReached,
+    Failed(String),
+}
+
+#[derive(Debug, Default)]
+struct State {
+    fetched: usize,
+    fetch: FetchState,
+    skipped: BTreeMap<Feature, (usize, String)>,
+}
+
+/// The run's access to objects outside the working tree, shared by every
+/// feature that reads them. Cloning shares the counts.
+#[derive(Debug, Clone, Default)]
+pub struct GitObjects {
+    layout: Arc<CloneLayout>,
+    git_dir: Option<PathBuf>,
+    workdir: Option<PathBuf>,
+    include_sparse_excluded: bool,
+    allow_fetch: bool,
+    fetch_limit: usize,
+    state: Arc<Mutex<State>>,
+}
+
+impl GitObjects {
+    /// Detect the layout of the repository containing `repo_root`; outside
+    /// a repository every check passes and nothing is fetched
+    pub fn open(repo_root: &Path, config: &GitConfig) -> Self {
+        let Ok(repo) = Repository::discover(repo_root) else {
+            return Self::default();
+        };
+        Self {
+            layout: Arc::new(CloneLayout::detect(&repo)),
+            git_dir: Some(repo.path().to_path_buf()),
+            workdir: repo.workdir().and_then(|dir| dir.canonicalize().ok()),
+            include_sparse_excluded: false,
+            allow_fetch: config.allow_fetch,
+            fetch_limit: config.fetch_limit,
+            state: Arc::default(),
+        }
+    }
+
+    /// [`open`](Self::open) without fetching: what a partial clone lacks is
+    /// skipped, whatever `[git]` says. The default of readers created
+    /// without the run's objects.
+    pub fn offline(repo_root: &Path) -> Self {
+        let config = GitConfig {
+            allow_fetch: false,
+            ..GitConfig::default()
+        };
+        Self::open(repo_root, &config)
+    }
+
+    /// Analyze the files a sparse checkout leaves out as well
+    /// (`--include-sparse-excluded`)
+    pub fn with_sparse_excluded(mut self, include: bool) -> Self {
+        self.include_sparse_excluded = include;
+        self
+    }
+
+    pub fn layout(&self) -> &CloneLayout {
+        &self.layout
+    }
+
+    pub fn is_partial(&self) -> bool {
+        self.layout.is_partial()
+    }
+
+    pub fn sparse(&self) -> Option<&SparseCheckout> {
+        self.layout.sparse.as_ref()
+    }
+
+    /// Whether `rel` (relative to the work tree, `/`-separated) is
+    /// analyzed: unless `--include-sparse-excluded` is given, not when a
+    /// sparse checkout leaves it out
+    pub fn in_checkout(&self, rel: &str) -> bool {
+        match self.sparse() {
+            Some(sparse) if !self.include_sparse_excluded => sparse.contains(rel),
+            _ => true,
+        }
+    }
+
+    /// Whether `e` is an object missing from the object database
+    pub fn is_missing(e: &git2::Error) -> bool {
+        e.class() == ErrorClass::Odb && e.code() == ErrorCode::NotFound
+    }
+
+    /// Make `oids` available as far as the policy allows and return the
+    /// ones still missing. Fetches once, for all of them. In a full clone
+    /// a missing object is an error: the repository is corrupt.
+    pub fn ensure(
+        &self,
+        repo: &Repository,
+        oids: impl IntoIterator<Item = Oid>,
+    ) -> Result<HashSet<Oid>> {
+        let odb = repo.odb()?;
+        let mut missing: HashSet<Oid> = oids
+            .into_iter()
+            .filter(|oid| !oid.is_zero() && !odb.exists(*oid))
+            .collect();
+        if missing.is_empty() {
+            return Ok(missing);
+        }
+        let Some(remote) = self.layout.promisor.as_deref() else {
+            let oid = missing.iter().next().copied().unwrap_or_else(Oid::zero);
+            return Err(anyhow!(
+                "object {} is missing from the repository, which is not a partial clone \
+                 — it may be corrupt (`git fsck`)",
+                oid
+            ));
+        };
+        if !self.allow_fetch {
+            return Ok(missing);
+        }
+
+        let batch: Vec<Oid> = {
+            let mut state = self.state.lock().unwrap();
+            if !matches!(state.fetch, FetchState::Ready) {
+                return Ok(missing);
+            }
+            if state.fetched + missing.len() > self.fetch_limit {
+                state.fetch = FetchState::LimitReached;
+                return Ok(missing);
+            }
+            state.fetched += missing.len();
+            missing.iter().copied().collect()
+        };
+        eprintln!(
+            "  Fetching {} object(s) missing from the partial clone from {}...",
+            batch.len(),
+            remote
+        );
+        if let Err(e) = self.fetch(remote, &batch) {
+            self.state.lock().unwrap().fetch = FetchState::Failed(format!("{:#}", e));
+            return Ok(missing);
+        }
+        odb.refresh()?;
+        missing.retain(|oid| !odb.exists(*oid));
+        Ok(missing)
+    }
+
+    /// Fetch `oids` from `remote` the way git's own on-demand fetch does
+    fn fetch(&self, remote: &str, oids: &[Oid]) -> Result<()> {
+        let git_dir = self
+            .git_dir
+            .as_deref()
+            .ok_or_else(|| anyhow!("no git directory"))?;
+        let mut child = Command::new("git")
+            .current_dir(git_dir)
+            .args(["-c", "fetch.negotiationAlgorithm=noop", "fetch", remote])
+            .args([
+                "--no-tags",
+                "--no-write-fetch-head",
+                "--recurse-submodules=no",
+            ])
+            .arg(format!(
+                "--filter={}",
+                self.layout.filter.as_deref().unwrap_or("blob:none")
+            ))
+            .arg("--stdin")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            for oid in oids {
+                writeln!(stdin, "{}", oid)?;
+            }
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!(
+                "{}",
+                stderr.lines().last().unwrap_or("git fetch failed").trim()
+            ));
+        }
+        Ok(())
+    }
+
+    /// The blob `oid`, fetched if allowed; `None` (counted against
+    /// `feature`) when the partial clone does not have it
+    pub fn blob<'r>(
+        &self,
+        repo: &'r Repository,
+        oid: Oid,
+        feature: Feature,
+    ) -> Result<Option<git2::Blob<'r>>> {
+        if !self.ensure(repo, [oid])?.is_empty() {
+            self.skip(feature, 1);
+            return Ok(None);
+        }
+        Ok(Some(repo.find_blob(oid)?))
+    }
+
+    /// Count `count` skips of `feature` for missing objects
+    pub fn skip(&self, feature: Feature, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        let reason = self.reason(&state.fetch);
+        let entry = state.skipped.entry(feature).or_insert((0, String::new()));
+        entry.0 += count;
+        entry.1 = reason;
+    }
+
+    /// Record that `feature` works on less than it would in a full clone,
+    /// without skipping anything in particular
+    pub fn narrow(&self, feature: Feature, reason: impl Into<String>) {
+        let mut state = self.state.lock().unwrap();
+        state.skipped.insert(feature, (0, reason.into()));
+    }
+
+    fn reason(&self, fetch: &FetchState) -> String {
+        if !self.allow_fetch {
+            return "not in the partial clone, and `[git] allow_fetch = false`".to_string();
+        }
+        match fetch {
+            FetchState::LimitReached => format!(
+                "not in the partial clone, past `[git] fetch_limit` ({} objects)",
+                self.fetch_limit
+            ),
+            FetchState::Failed(e) => format!("not in the partial clone, fetch failed: {}", e),
+            FetchState::Ready => "not in the partial clone".to_string(),
+        }
+    }
+
+    /// Every feature that skipped something, in a fixed order
+    pub fn degradations(&self) -> Vec<Degradation> {
+        self.state
+            .lock()
+            .unwrap()
+            .skipped
+            .iter()
+            .map(|(feature, (skipped, reason))| Degradation {
+                feature: *feature,
+                skipped: *skipped,
+                reason: reason.clone(),
+            })
+            .collect()
+    }
+
+    /// Objects fetched so far
+    pub fn fetched(&self) -> usize {
+        self.state.lock().unwrap().fetched
+    }
+
+    /// Drop the `files` (absolute, under `repo_root`) a sparse checkout
+    /// leaves out, recording each in `ledger`
+    pub fn retain_sparse(
+        &self,
+        repo_root: &Path,
+        files: Vec<PathBuf>,
+        mut ledger: Option<&mut CoverageLedger>,
+    ) -> Vec<PathBuf> {
+        if self.sparse().is_none() || self.include_sparse_excluded {
+            return files;
+        }
+        let workdir = self.workdir.as_deref().unwrap_or(repo_root);
+        files
+            .into_iter()
+            .filter(|file| {
+                let rel = file.strip_prefix(workdir).unwrap_or(file);
+                let rel = rel.to_string_lossy().replace('\\', "/");
+                if self.in_checkout(&rel) {
+                    return true;
+                }
+                if let Some(ledger) = ledger.as_deref_mut() {
+                    ledger.dropped(file, SkipReason::SparseExcluded);
+                }
+                false
+            })
+            .collect()
+    }
+}
+
+/// Blob ids a diff needs for its changed lines, per file path. The new
+/// side is left out when it is the working tree (`worktree`), which is read
+/// from disk, and deleted files are left out altogether: they have no
+/// changed lines, and a sparse checkout's excluded files diff as deleted
+/// against the working tree.
+pub fn diff_blobs(diff: &git2::Diff<'_>, worktree: bool) -> HashMap<PathBuf, Vec<Oid>> {
+    let mut blobs: HashMap<PathBuf, Vec<Oid>> = HashMap::new();
+    for delta in diff.deltas() {
+        if delta.status() == git2::Delta::Deleted {
+            continue;
+        }
+        let Some(path) = delta.new_file().path().or(delta.old_file().path()) else {
+            continue;
+        };
+        let sides = if worktree {
+            vec![delta.old_file()]
+        } else {
+            vec![delta.new_file(), delta.old_file()]
+        };
+        let ids = blobs.entry(path.to_path_buf()).or_default();
+        for file in sides {
+            if !file.id().is_zero() && file.mode() != git2::FileMode::Commit {
+                ids.push(file.id());
+            }
+        }
+    }
+    blobs
+}
@@ -1,5 +1,6 @@
 //! Edge types for the code graph
 
+use crate::finding::Confidence;
 use serde::{Deserialize, Serialize};
 
 /// An edge in the code graph representing a relationship between code entities
@@ -27,6 +28,22 @@ impl Edge {
         }
     }
 
+    /// An `Imports` edge classified as `kind`; runtime imports carry no
+    /// metadata
+    pub fn import(kind: ImportKind) -> Self {
+        if kind == ImportKind::Runtime {
+            return Self::new(EdgeKind::Imports);
+        }
+        Self::with_metadata(
+            EdgeKind::Imports,
+            EdgeMetadata::Import {
+                alias: None,
+                is_wildcard: false,
+                kind,
+            },
+        )
+    }
+
     pub fn kind(&self) -> &EdgeKind {
         &self.kind
     }
@@ -34,6 +51,24 @@ impl Edge {
     pub fn metadata(&self) -> Option<&EdgeMetadata> {
         self.metadata.as_ref()
     }
+
+    /// Classification of an import edge; [`ImportKind::Runtime`] when the
+    /// edge has no import metadata
+    pub fn import_kind(&self) -> ImportKind {
+        match &self.metadata {
+            Some(EdgeMetadata::Import { kind, .. }) => *kind,
+            _ => ImportKind::Runtime,
+        }
+    }
+
+    /// Reclassify an import edge, keeping its alias and wildcard flag
+    pub fn set_import_kind(&mut self, kind: ImportKind) {
+        match &mut self.metadata {
+            Some(EdgeMetadata::Import { kind: k, .. }) => *k = kind,
+            _ if kind == ImportKind::Runtime => {}
+            _ => *self = Self::import(kind),
+        }
+    }
 }
 
 /// The kind of relationship an edge represents
@@ -79,10 +114,68 @@ pub enum EdgeMetadata {
         alias: Option<String>,
         /// Whether this is a wildcard import
         is_wildcard: bool,
+        /// What the import is needed for
+        #[serde(default, skip_serializing_if = "ImportKind::is_runtime")]
+        kind: ImportKind,
+    },
+    /// A call into a module outside the repository (standard library or a
+    /// package), on a `Calls` edge from the calling function to the
+    /// [`Import`](super::NodeKind::Import) node the callee came in through
+    ExternalCall {
+        /// Line number where the call occurs
+        line: usize,
+        /// Name called, relative to the imported module: `get_event_loop`
+        /// for `asyncio.get_event_loop()`
+        callee: String,
     },
     /// Type reference metadata
     TypeRef {
         /// Parameter index (for AcceptsParam edges)
         param_index: Option<usize>,
     },
+    /// A reference the framework wires up rather than the code, e.g. a
+    /// FastAPI `Depends(get_db)` or a pytest fixture parameter
+    Reference {
+        /// Line of the referencing entity
+        line: usize,
+        /// How sure the resolution is; name-based matches are `Medium`
+        confidence: Confidence,
+    },
+}
+
+/// What an import is needed for, where the parser or resolver can tell
+///
+/// - `type_only` — erased at runtime: TypeScript `import type` or an
+///   import whose names are all `type`, Python imports under
+///   `if TYPE_CHECKING:`
+/// - `test` — the imported file is test code
+/// - `dev` — the imported package is a dev dependency of the importing
+///   file's manifest (`devDependencies`, `[dev-dependencies]`, Poetry dev
+///   groups, `[dependency-groups]`)
+/// - `runtime` — everything else
+///
+/// A type-only import stays type-only whatever it imports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportKind {
+    #[default]
+    Runtime,
+    TypeOnly,
+    Dev,
+    Test,
+}
+
+impl ImportKind {
+    pub fn is_runtime(&self) -> bool {
+        *self == Self::Runtime
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Runtime => "runtime",
+            Self::TypeOnly => "type_only",
+            Self::Dev => "dev",
+            Self::Test => "test",
+        }
+    }
 }
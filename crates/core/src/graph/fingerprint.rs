@@ -0,0 +1,311 @@
+//! Stable node identity across runs
+//!
+//! [`NodeId`]s follow parse order, so they say nothing about whether a node
+//! of one graph is "the same function" as a node of another. A
+//! [`NodeFingerprint`] does: it is derived from
+//!
+//! - the language of the node's file
+//! - the file path relative to the graph root
+//! - the node kind
+//! - the qualified name: the names of the enclosing classes, functions and
+//!   modules (by [`EdgeKind::Contains`] within the file, or else the
+//!   innermost class whose lines span the node) joined with `.`
+//! - for functions, the signature with whitespace removed: parameters with
+//!   their types and defaults, return type and type parameters
+//!
+//! Line numbers are not part of it, so edits that only move code (blank
+//! lines, comments, reformatting) keep every fingerprint. Nodes that agree
+//! on all of the above, like two same-named definitions in one file, are
+//! told apart by an ordinal assigned in source order.
+//!
+//! A renamed or moved symbol gets a new fingerprint. Rename and move
+//! detection belongs to the matchers built on top, such as
+//! [`Changelog`](crate::diff::Changelog).
+
+use super::{CodeGraph, EdgeKind, Node, NodeData, NodeId, NodeKind};
+use anyhow::{anyhow, Context};
+use petgraph::visit::EdgeRef;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Canonical identity of a node, stable across runs and parse orders.
+///
+/// Written as `<symbol>:<signature>:<ordinal>`, the first two as 16 hex
+/// digits; the signature digest is zero for nodes that are not functions.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(into = "String", try_from = "String")]
+pub struct NodeFingerprint {
+    /// Digest of language, relative path, kind and qualified name
+    symbol: u64,
+    /// Digest of the normalized signature, 0 without one
+    signature: u64,
+    /// Position among the nodes sharing `symbol` and `signature`, in
+    /// source order
+    ordinal: u32,
+}
+
+impl NodeFingerprint {
+    /// Whether `other` names the same symbol, whatever its signature
+    pub fn same_symbol(&self, other: &NodeFingerprint) -> bool {
+        self.symbol == other.symbol
+    }
+
+    /// Position among otherwise identical nodes, in source order
+    pub fn ordinal(&self) -> u32 {
+        self.ordinal
+    }
+}
+
+impl fmt::Display for NodeFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:016x}:{:016x}:{}",
+            self.symbol, self.signature, self.ordinal
+        )
+    }
+}
+
+impl FromStr for NodeFingerprint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut parts = s.split(':');
+        let mut next = || {
+            parts
+                .next()
+                .ok_or_else(|| anyhow!("invalid fingerprint {:?}", s))
+        };
+        let symbol = u64::from_str_radix(next()?, 16).context("invalid fingerprint symbol")?;
+        let signature =
+            u64::from_str_radix(next()?, 16).context("invalid fingerprint signature")?;
+        let ordinal = next()?.parse().context("invalid fingerprint ordinal")?;
+        if parts.next().is_some() {
+            return Err(anyhow!("invalid fingerprint {:?}", s));
+        }
+        Ok(Self {
+            symbol,
+            signature,
+            ordinal,
+        })
+    }
+}
+
+impl From<NodeFingerprint> for String {
+    fn from(fingerprint: NodeFingerprint) -> Self {
+        fingerprint.to_string()
+    }
+}
+
+impl TryFrom<String> for NodeFingerprint {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> anyhow::Result<Self> {
+        s.parse()
+    }
+}
+
+/// Fingerprints of every node of `graph`, by node index
+pub(super) fn compute(graph: &CodeGraph) -> Vec<NodeFingerprint> {
+    let inner = graph.inner_graph();
+
+    let mut languages: HashMap<&Path, &str> = HashMap::new();
+    for (_, node) in graph.nodes() {
+        if let NodeData::File { language, .. } = node.data() {
+            languages.insert(node.file_path(), language);
+        }
+    }
+    // The symbol enclosing each node in its own file
+    let mut parents: HashMap<NodeId, NodeId> = HashMap::new();
+    for edge in inner.edge_references() {
+        let (parent, child) = (&inner[edge.source()], &inner[edge.target()]);
+        if edge.weight().kind() == &EdgeKind::Contains
+            && parent.kind() != &NodeKind::File
+            && parent.file_path() == child.file_path()
+        {
+            parents.entry(edge.target()).or_insert(edge.source());
+        }
+    }
+    // Parsers that record methods only in their class's data leave them
+    // without a Contains edge: the innermost class spanning their line
+    // encloses them instead
+    let mut classes: HashMap<&Path, Vec<(usize, usize, NodeId)>> = HashMap::new();
+    for (id, node) in graph.nodes() {
+        if let (NodeKind::Class | NodeKind::Interface, Some(end)) = (node.kind(), node.end_line()) {
+            classes
+                .entry(node.file_path())
+                .or_default()
+                .push((node.line(), end, id));
+        }
+    }
+    for (id, node) in graph.nodes() {
+        if node.kind() == &NodeKind::File || parents.contains_key(&id) {
+            continue;
+        }
+        let enclosing = classes
+            .get(node.file_path().as_path())
+            .into_iter()
+            .flatten()
+            .filter(|(start, end, class)| *class != id && (*start..=*end).contains(&node.line()))
+            .max_by_key(|(start, _, class)| (*start, std::cmp::Reverse(*class)));
+        if let Some(&(_, _, class)) = enclosing {
+            parents.insert(id, class);
+        }
+    }
+
+    let keys: Vec<(u64, u64)> = graph
+        .nodes()
+        .map(|(id, node)| {
+            let path = node.file_path();
+            let language = languages
+                .get(path.as_path())
+                .copied()
+                .or_else(|| path.extension().and_then(|e| e.to_str()))
+                .unwrap_or_default();
+            let relative = path.strip_prefix(graph.root_path()).unwrap_or(path);
+            let relative: Vec<_> = relative.iter().map(|c| c.to_string_lossy()).collect();
+            let symbol = digest(&[
+                language,
+                &relative.join("/"),
+                &format!("{:?}", node.kind()),
+                &qualified_name(graph, &parents, id),
+            ]);
+            let signature = signature(node).map_or(0, |s| digest(&[&s]));
+            (symbol, signature)
+        })
+        .collect();
+
+    // Ordinals among equal keys, by line and then parse order
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    order.sort_by_key(|&i| (keys[i], inner[NodeId::new(i)].line(), i));
+    let mut fingerprints = vec![NodeFingerprint::default(); keys.len()];
+    let mut ordinal = 0;
+    for (n, &i) in order.iter().enumerate() {
+        ordinal = if n > 0 && keys[order[n - 1]] == keys[i] {
+            ordinal + 1
+        } else {
+            0
+        };
+        fingerprints[i] = NodeFingerprint {
+            symbol: keys[i].0,
+            signature: keys[i].1,
+            ordinal,
+        };
+    }
+    fingerprints
+}
+
+/// `Outer.Inner.name`, following enclosing symbols. Names some parsers
+/// already qualify (`Outer.name` inside `Outer`) are not qualified twice.
+fn qualified_name(graph: &CodeGraph, parents: &HashMap<NodeId, NodeId>, id: NodeId) -> String {
+    let mut name = graph.inner_graph()[id].name().to_string();
+    let mut current = id;
+    // Bounded, in case of a Contains cycle
+    for _ in 0..parents.len() {
+        let Some(&parent) = parents.get(&current) else {
+            break;
+        };
+        let outer = graph.inner_graph()[parent].name();
+        if !name
+            .strip_prefix(outer)
+            .is_some_and(|rest| rest.starts_with('.'))
+        {
+            name = format!("{}.{}", outer, name);
+        }
+        current = parent;
+    }
+    name
+}
+
+/// A function's parameters, return type and type parameters with all
+/// whitespace removed; `None` for other nodes
+fn signature(node: &Node) -> Option<String> {
+    let NodeData::Function {
+        parameters,
+        return_type,
+    } = node.data()
+    else {
+        return None;
+    };
+    let params: Vec<String> = parameters
+        .iter()
+        .map(|p| {
+            format!(
+                "{}:{}={}",
+                p.name,
+                p.param_type.as_deref().unwrap_or_default(),
+                p.default_value.as_deref().unwrap_or_default()
+            )
+        })
+        .collect();
+    let signature = format!(
+        "<{}>({})->{}",
+        node.type_parameters().join(","),
+        params.join(","),
+        return_type.as_deref().unwrap_or_default()
+    );
+    Some(signature.split_whitespace().collect())
+}
+
+/// First 8 bytes of the SHA-256 of `parts`, NUL-separated
+fn digest(parts: &[&str]) -> u64 {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    let hash = hasher.finalize();
+    u64::from_be_bytes(hash[..8].try_into().expect("SHA-256 is 32 bytes"))
+}
+
+/// Pair the nodes of two versions of a graph: equal fingerprints first,
+/// then, in the order given, nodes of the same symbol whose signature
+/// changed. Returns every key of `new` with its counterpart in `old`.
+pub fn pair<'f, K: Copy>(
+    old: impl IntoIterator<Item = (K, &'f NodeFingerprint)>,
+    new: impl IntoIterator<Item = (K, &'f NodeFingerprint)>,
+) -> Vec<(K, Option<K>)> {
+    let old: Vec<(K, &NodeFingerprint)> = old.into_iter().collect();
+    let mut exact: HashMap<&NodeFingerprint, usize> = HashMap::new();
+    for (i, (_, fingerprint)) in old.iter().enumerate() {
+        exact.entry(*fingerprint).or_insert(i);
+    }
+
+    let mut taken = vec![false; old.len()];
+    let mut pairs: Vec<(K, &NodeFingerprint, Option<usize>)> = new
+        .into_iter()
+        .map(|(key, fingerprint)| {
+            let found = exact.remove(fingerprint);
+            if let Some(o) = found {
+                taken[o] = true;
+            }
+            (key, fingerprint, found)
+        })
+        .collect();
+
+    let mut by_symbol: HashMap<u64, VecDeque<usize>> = HashMap::new();
+    for (i, (_, fingerprint)) in old.iter().enumerate() {
+        if !taken[i] {
+            by_symbol
+                .entry(fingerprint.symbol)
+                .or_default()
+                .push_back(i);
+        }
+    }
+    for (_, fingerprint, found) in pairs.iter_mut().filter(|p| p.2.is_none()) {
+        *found = by_symbol
+            .get_mut(&fingerprint.symbol)
+            .and_then(VecDeque::pop_front);
+    }
+
+    pairs
+        .into_iter()
+        .map(|(key, _, found)| (key, found.map(|o| old[o].0)))
+        .collect()
+}
@@ -1,17 +1,21 @@
 //! Code dependency graph data structures and operations
 
 pub mod edges;
+pub mod fingerprint;
 pub mod nodes;
 pub mod query;
+pub mod validate;
 
-pub use edges::{Edge, EdgeKind, EdgeMetadata};
-pub use nodes::{Node, NodeData, NodeKind, Parameter};
+pub use edges::{Edge, EdgeKind, EdgeMetadata, ImportKind};
+pub use fingerprint::NodeFingerprint;
+pub use nodes::{Doc, Node, NodeData, NodeKind, Parameter};
 pub use query::GraphQuery;
+pub use validate::Violation;
 
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 /// Result of merging another graph into this one.
@@ -48,12 +52,26 @@ impl CodeGraph {
         }
     }
 
+    /// Assemble a graph from an already-built petgraph and its lookup index
+    /// (keys are `"{file}:{name}"`, as built by [`CodeGraph::add_node`])
+    pub(crate) fn from_parts(
+        root_path: PathBuf,
+        graph: DiGraph<Node, Edge>,
+        node_index: HashMap<String, Vec<NodeId>>,
+    ) -> Self {
+        Self {
+            graph,
+            node_index,
+            root_path,
+        }
+    }
+
     /// Add a node to the graph
     pub fn add_node(&mut self, node: Node) -> NodeId {
-        let node_id = self.graph.add_node(node.clone());
+        let key = format!("{}:{}", node.file_path().display(), node.name());
+        let node_id = self.graph.add_node(node);
 
         // Update the index
-        let key = format!("{}:{}", node.file_path().display(), node.name());
         self.node_index.entry(key).or_default().push(node_id);
 
         node_id
@@ -64,6 +82,12 @@ impl CodeGraph {
         self.graph.add_edge(from, to, edge);
     }
 
+    /// The first edge from `from` to `to`, mutably
+    pub fn edge_mut(&mut self, from: NodeId, to: NodeId) -> Option<&mut Edge> {
+        let edge = self.graph.find_edge(from, to)?;
+        self.graph.edge_weight_mut(edge)
+    }
+
     /// Get a node by its ID
     pub fn node(&self, id: NodeId) -> Option<&Node> {
         self.graph.node_weight(id)
@@ -117,14 +141,62 @@ impl CodeGraph {
     }
 
     /// Remove a node from the graph
+    ///
+    /// The last node takes over the removed node's ID, so IDs above `node`
+    /// held by the caller are not stable across this call.
     pub fn remove_node(&mut self, node: NodeId) -> Option<Node> {
-        if let Some(node_data) = self.graph.node_weight(node) {
-            let key = format!("{}:{}", node_data.file_path().display(), node_data.name());
-            if let Some(ids) = self.node_index.get_mut(&key) {
-                ids.retain(|&id| id != node);
+        let node_data = self.graph.node_weight(node)?;
+        let key = format!("{}:{}", node_data.file_path().display(), node_data.name());
+        if let Some(ids) = self.node_index.get_mut(&key) {
+            ids.retain(|&id| id != node);
+        }
+        let last = NodeId::new(self.graph.node_count() - 1);
+        let removed = self.graph.remove_node(node);
+        if last != node {
+            if let Some(moved) = self.graph.node_weight(node) {
+                let key = format!("{}:{}", moved.file_path().display(), moved.name());
+                if let Some(ids) = self.node_index.get_mut(&key) {
+                    for id in ids.iter_mut().filter(|id| **id == last) {
+                        *id = node;
+                    }
+                }
             }
         }
-        self.graph.remove_node(node)
+        removed
+    }
+
+    /// Remove `file`'s File node and every node it contains, with all
+    /// their edges.
+    ///
+    /// In a valid graph those are the nodes reachable from the File node
+    /// through [`EdgeKind::Contains`] edges, and its imports; nodes of the
+    /// file a parser left uncontained are removed too.
+    ///
+    /// Kept nodes take over the freed IDs (see [`CodeGraph::remove_node`]);
+    /// the returned [`MergeMap`] maps the old ID of every kept node that
+    /// moved to its new one, for [`ParseState::remap`](crate::ParseState::remap).
+    pub fn remove_file(&mut self, file: &Path) -> MergeMap {
+        let mut doomed: Vec<NodeId> = self
+            .graph
+            .node_indices()
+            .filter(|&id| self.graph[id].file_path() == file)
+            .collect();
+
+        // Highest first: every node moved into a freed slot is then a kept one
+        doomed.sort_unstable_by(|a, b| b.cmp(a));
+        let mut origin: HashMap<NodeId, NodeId> = HashMap::new();
+        for id in doomed {
+            let last = NodeId::new(self.graph.node_count() - 1);
+            self.remove_node(id);
+            if last != id {
+                let first = origin.remove(&last).unwrap_or(last);
+                origin.insert(id, first);
+            }
+        }
+        origin
+            .into_iter()
+            .map(|(now, before)| (before, now))
+            .collect()
     }
 
     /// Get the root path of the codebase
@@ -170,6 +242,110 @@ impl CodeGraph {
         id_map
     }
 
+    /// The nodes of `file`, every node one edge away from them, and the
+    /// edges touching `file`'s nodes, as a graph of its own
+    pub fn neighbourhood(&self, file: &Path) -> CodeGraph {
+        let own: Vec<NodeId> = self
+            .graph
+            .node_indices()
+            .filter(|&id| self.graph[id].file_path() == file)
+            .collect();
+        let edges: Vec<_> = self
+            .graph
+            .edge_references()
+            .filter(|e| own.contains(&e.source()) || own.contains(&e.target()))
+            .collect();
+        let mut wanted: Vec<NodeId> = own.clone();
+        wanted.extend(edges.iter().flat_map(|e| [e.source(), e.target()]));
+        wanted.sort_unstable();
+        wanted.dedup();
+
+        let mut sub = CodeGraph::new(self.root_path.clone());
+        let ids: HashMap<NodeId, NodeId> = wanted
+            .into_iter()
+            .map(|id| (id, sub.add_node(self.graph[id].clone())))
+            .collect();
+        for edge in edges {
+            sub.add_edge(
+                ids[&edge.source()],
+                ids[&edge.target()],
+                edge.weight().clone(),
+            );
+        }
+        sub
+    }
+
+    /// The nodes of `files` and the edges between them, as a graph of
+    /// their own
+    pub fn restricted_to(&self, files: &HashSet<PathBuf>) -> CodeGraph {
+        let mut sub = CodeGraph::new(self.root_path.clone());
+        let ids: HashMap<NodeId, NodeId> = self
+            .graph
+            .node_indices()
+            .filter(|&id| files.contains(self.graph[id].file_path()))
+            .map(|id| (id, sub.add_node(self.graph[id].clone())))
+            .collect();
+        for edge in self.graph.edge_references() {
+            if let (Some(&from), Some(&to)) = (ids.get(&edge.source()), ids.get(&edge.target())) {
+                sub.add_edge(from, to, edge.weight().clone());
+            }
+        }
+        sub
+    }
+
+    /// A copy of the graph rooted at `root`, with every node's file path
+    /// passed through `relocate`
+    pub fn relocated(&self, root: PathBuf, relocate: impl Fn(&Path) -> PathBuf) -> CodeGraph {
+        let mut moved = CodeGraph::new(root);
+        for id in self.graph.node_indices() {
+            let mut node = self.graph[id].clone();
+            node.set_file_path(relocate(node.file_path()));
+            moved.add_node(node);
+        }
+        for edge in self.graph.edge_references() {
+            moved.add_edge(edge.source(), edge.target(), edge.weight().clone());
+        }
+        moved
+    }
+
+    /// The fingerprint of every node, by node index: the assigned ones, or
+    /// all of them computed afresh when any node has none
+    pub fn fingerprints(&self) -> Vec<NodeFingerprint> {
+        let assigned: Option<Vec<NodeFingerprint>> = self
+            .graph
+            .node_weights()
+            .map(|n| n.fingerprint().copied())
+            .collect();
+        assigned.unwrap_or_else(|| fingerprint::compute(self))
+    }
+
+    /// Compute every node's [`NodeFingerprint`] and store it on the node
+    pub fn assign_fingerprints(&mut self) {
+        for (i, fingerprint) in fingerprint::compute(self).into_iter().enumerate() {
+            self.graph[NodeId::new(i)].set_fingerprint(fingerprint);
+        }
+    }
+
+    /// Assign fingerprints unless every node has one already, as in graphs
+    /// loaded from caches and stores written before fingerprints existed
+    pub fn ensure_fingerprints(&mut self) {
+        if self.graph.node_weights().any(|n| n.fingerprint().is_none()) {
+            self.assign_fingerprints();
+        }
+    }
+
+    /// Check the graph's structural invariants (see [`validate`]): every
+    /// non-file node is contained in its file, and no edge or index entry
+    /// refers to a missing node
+    pub fn validate(&self) -> Result<(), Vec<Violation>> {
+        let violations = validate::check(self);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
     /// Get a query interface for complex graph operations
     pub fn query(&self) -> GraphQuery<'_> {
         GraphQuery::new(self)
@@ -1,5 +1,7 @@
 //! Node types for the code graph
 
+use super::NodeFingerprint;
+use crate::code_kind::CodeKind;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -27,18 +29,30 @@ pub struct Node {
     /// Whether this symbol is publicly visible outside its module.
     /// Defaults to `true` for parsers that don't track visibility (conservative:
     /// avoids false-silencing dead-code findings on unknown-visibility symbols).
-    /// NOTE: must be before `decorators`/`type_parameters` so msgpack array
-    /// serialisation (positional) stays consistent when those fields are omitted.
+    /// NOTE: msgpack array serialisation is positional, so fields are only
+    /// ever added at the end, always serialised, and defaulted when an older
+    /// cache ends before them.
     #[serde(default = "default_true")]
     is_public: bool,
 
     /// Decorators/annotations applied to this entity
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
     decorators: Vec<String>,
 
     /// Type parameters (generics) on this entity, e.g. ["T", "T extends Foo", "T = string"]
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
     type_parameters: Vec<String>,
+
+    /// Stable identity across runs, set by [`CodeGraph::assign_fingerprints`]
+    ///
+    /// [`CodeGraph::assign_fingerprints`]: super::CodeGraph::assign_fingerprints
+    #[serde(default)]
+    fingerprint: Option<NodeFingerprint>,
+
+    /// The documentation comment immediately preceding the declaration (or
+    /// the docstring opening its body), as recorded by the parser
+    #[serde(default)]
+    doc: Doc,
 }
 
 impl Node {
@@ -59,6 +73,8 @@ impl Node {
             is_public: true,
             decorators: Vec::new(),
             type_parameters: Vec::new(),
+            fingerprint: None,
+            doc: Doc::Unrecorded,
         }
     }
 
@@ -74,6 +90,10 @@ impl Node {
         &self.file_path
     }
 
+    pub fn set_file_path(&mut self, file_path: PathBuf) {
+        self.file_path = file_path;
+    }
+
     pub fn line(&self) -> usize {
         self.line
     }
@@ -123,6 +143,55 @@ impl Node {
     pub fn set_is_public(&mut self, is_public: bool) {
         self.is_public = is_public;
     }
+
+    /// Stable identity of this node across runs; `None` until the graph
+    /// assigns fingerprints
+    pub fn fingerprint(&self) -> Option<&NodeFingerprint> {
+        self.fingerprint.as_ref()
+    }
+
+    /// Set the stable identity of this node
+    pub fn set_fingerprint(&mut self, fingerprint: NodeFingerprint) {
+        self.fingerprint = Some(fingerprint);
+    }
+
+    /// The documentation comment of this entity, if its parser records them
+    pub fn doc(&self) -> &Doc {
+        &self.doc
+    }
+
+    /// Record the documentation comment of this entity
+    pub fn set_doc(&mut self, doc: Doc) {
+        self.doc = doc;
+    }
+}
+
+/// Whether a declaration carries a documentation comment
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Doc {
+    /// The parser doesn't record documentation comments (or the node was
+    /// cached before it did), so nothing is known either way
+    #[default]
+    Unrecorded,
+    /// No documentation comment precedes the declaration
+    Missing,
+    /// Documented; holds the first line of the comment without its markers
+    Present(String),
+}
+
+impl Doc {
+    /// Whether the parser recorded this entity's documentation at all
+    pub fn is_recorded(&self) -> bool {
+        !matches!(self, Doc::Unrecorded)
+    }
+
+    /// First line of the documentation comment, when there is one
+    pub fn summary(&self) -> Option<&str> {
+        match self {
+            Doc::Present(line) => Some(line),
+            _ => None,
+        }
+    }
 }
 
 fn default_true() -> bool {
@@ -131,6 +200,7 @@ fn default_true() -> bool {
 
 /// The kind of code entity a node represents
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum NodeKind {
     /// A source file
     File,
@@ -161,6 +231,9 @@ pub enum NodeKind {
 pub enum NodeData {
     File {
         language: String,
+        /// Built-in production/test/script classification
+        #[serde(default)]
+        code_kind: CodeKind,
     },
     Module {
         exports: Vec<String>,
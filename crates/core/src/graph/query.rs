@@ -2,6 +2,7 @@
 
 use super::{CodeGraph, EdgeKind, NodeId};
 use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
 
 /// A query interface for complex graph operations
 pub struct GraphQuery<'a> {
@@ -50,6 +51,19 @@ impl<'a> GraphQuery<'a> {
 
     /// Find all nodes that transitively call the given node (via `Calls` edges only).
     pub fn transitive_callers(&self, node: NodeId, max_depth: Option<usize>) -> Vec<NodeId> {
+        self.transitive_callers_with_depth(node, max_depth)
+            .into_iter()
+            .map(|(caller, _)| caller)
+            .collect()
+    }
+
+    /// Like [`transitive_callers`](Self::transitive_callers), paired with each
+    /// caller's BFS depth (1 = direct caller).
+    pub fn transitive_callers_with_depth(
+        &self,
+        node: NodeId,
+        max_depth: Option<usize>,
+    ) -> Vec<(NodeId, usize)> {
         let mut result = Vec::new();
         let mut visited = HashSet::new();
         let mut queue = VecDeque::new();
@@ -71,7 +85,7 @@ impl<'a> GraphQuery<'a> {
                 .filter(|(_, e)| e.kind() == &EdgeKind::Calls)
             {
                 if visited.insert(caller) {
-                    result.push(caller);
+                    result.push((caller, depth + 1));
                     queue.push_back((caller, depth + 1));
                 }
             }
@@ -183,4 +197,25 @@ impl<'a> GraphQuery<'a> {
             .map(|(source, _)| source)
             .collect()
     }
+
+    /// `files` plus the files of every node one edge away from theirs: what
+    /// they import and call, and what imports and calls them
+    pub fn adjacent_files(&self, files: &HashSet<PathBuf>) -> HashSet<PathBuf> {
+        let mut adjacent = files.clone();
+        for (id, node) in self.graph.nodes() {
+            if !files.contains(node.file_path()) {
+                continue;
+            }
+            for other in self
+                .dependencies(id)
+                .into_iter()
+                .chain(self.direct_dependents(id))
+            {
+                if let Some(other) = self.graph.node(other) {
+                    adjacent.insert(other.file_path().clone());
+                }
+            }
+        }
+        adjacent
+    }
 }
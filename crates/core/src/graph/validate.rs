@@ -0,0 +1,140 @@
+//! Structural invariants of a code graph
+//!
+//! Whatever a parser extracts, the graph it leaves behind must hold
+//! together:
+//!
+//! - every node other than a file is reachable from its file's
+//!   [`NodeKind::File`] node through [`EdgeKind::Contains`] edges — an
+//!   import node through the [`EdgeKind::Imports`] edge of the file (or
+//!   scope) holding the statement
+//! - no edge and no lookup index entry refers to a node that does not exist
+//!
+//! [`CodeGraph::validate`] checks both. The parser conformance and fuzz
+//! harnesses run it on every graph they build, and debug builds assert it
+//! after each file is parsed.
+
+use super::{CodeGraph, EdgeKind, NodeId, NodeKind};
+use petgraph::visit::EdgeRef;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::path::PathBuf;
+
+/// A broken invariant found by [`CodeGraph::validate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// A node no `Contains` path from its file's `File` node reaches (for
+    /// an import node, `Contains` path and `Imports` edge)
+    Uncontained {
+        node: NodeId,
+        name: String,
+        file: PathBuf,
+    },
+    /// A node of a file the graph has no `File` node for
+    MissingFile { node: NodeId, file: PathBuf },
+    /// An edge whose source or target is not a node of the graph
+    DanglingEdge { from: NodeId, to: NodeId },
+    /// A lookup index entry for a node that does not exist or has another
+    /// file and name
+    StaleIndex { key: String, node: NodeId },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Uncontained { node, name, file } => write!(
+                f,
+                "node {} `{}` in {} is not contained in its file",
+                node.index(),
+                name,
+                file.display()
+            ),
+            Violation::MissingFile { node, file } => write!(
+                f,
+                "node {} belongs to {}, which has no File node",
+                node.index(),
+                file.display()
+            ),
+            Violation::DanglingEdge { from, to } => write!(
+                f,
+                "edge {} -> {} refers to a missing node",
+                from.index(),
+                to.index()
+            ),
+            Violation::StaleIndex { key, node } => {
+                write!(f, "index entry `{}` refers to node {}", key, node.index())
+            }
+        }
+    }
+}
+
+pub(super) fn check(graph: &CodeGraph) -> Vec<Violation> {
+    let inner = &graph.graph;
+    let count = inner.node_count();
+    let mut violations = Vec::new();
+
+    let mut contains: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    for edge in inner.edge_references() {
+        let (from, to) = (edge.source(), edge.target());
+        if from.index() >= count || to.index() >= count {
+            violations.push(Violation::DanglingEdge { from, to });
+        } else if *edge.weight().kind() == EdgeKind::Contains
+            || (*edge.weight().kind() == EdgeKind::Imports && *inner[to].kind() == NodeKind::Import)
+        {
+            contains.entry(from).or_default().push(to);
+        }
+    }
+
+    for (key, ids) in &graph.node_index {
+        for &node in ids {
+            let matches = inner
+                .node_weight(node)
+                .is_some_and(|n| format!("{}:{}", n.file_path().display(), n.name()) == *key);
+            if !matches {
+                violations.push(Violation::StaleIndex {
+                    key: key.clone(),
+                    node,
+                });
+            }
+        }
+    }
+
+    let mut files: HashMap<&PathBuf, Vec<NodeId>> = HashMap::new();
+    for id in inner.node_indices() {
+        if *inner[id].kind() == NodeKind::File {
+            files.entry(inner[id].file_path()).or_default().push(id);
+        }
+    }
+
+    let mut reached: HashSet<NodeId> = HashSet::new();
+    let mut queue: VecDeque<NodeId> = files.values().flatten().copied().collect();
+    while let Some(id) = queue.pop_front() {
+        for &child in contains.get(&id).into_iter().flatten() {
+            // Contains edges between files (directories, packages) do not
+            // make one file's nodes part of another's
+            if inner[child].file_path() == inner[id].file_path() && reached.insert(child) {
+                queue.push_back(child);
+            }
+        }
+    }
+
+    for id in inner.node_indices() {
+        let node = &inner[id];
+        if *node.kind() == NodeKind::File || reached.contains(&id) {
+            continue;
+        }
+        if files.contains_key(node.file_path()) {
+            violations.push(Violation::Uncontained {
+                node: id,
+                name: node.name().to_string(),
+                file: node.file_path().clone(),
+            });
+        } else {
+            violations.push(Violation::MissingFile {
+                node: id,
+                file: node.file_path().clone(),
+            });
+        }
+    }
+
+    violations
+}
@@ -0,0 +1,305 @@
+//! Task-runner hash of a monorepo project's inputs — `revet hash-inputs`
+//!
+//! Nx and Turborepo skip a cached task when the hash of its inputs is
+//! unchanged. [`HashInputs`] collects everything that could change a
+//! project's findings, and [`HashInputs::digest`] hashes it:
+//!
+//! - the discovered files of the project and of the projects it depends
+//!   on (`[[project]] depends_on`, followed transitively), each path with
+//!   the hash of its content
+//! - the resolved configuration: the profile settings and the
+//!   `.revet.toml` sections analyzers read
+//! - the analyzers that run and their versions
+//! - the baseline's content
+//! - the `HEAD` commit, only when a module reads git history
+//!
+//! The digest is the same on every machine: paths are relative to the
+//! repository root with `/` separators, and line endings are normalized to
+//! `\n` before content is hashed.
+//!
+//! A project is a directory holding one of the changelog's
+//! [`MANIFESTS`], or one listed as a `[[project]]`.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::RevetConfig;
+use crate::diff::changelog::MANIFESTS;
+use crate::profile::ProfileSettings;
+
+/// Version of the inputs' layout, hashed along with them so a change to
+/// what is collected invalidates earlier digests
+pub const FORMAT_VERSION: u32 = 1;
+
+/// `.revet.toml` sections that can't change findings: where state is kept,
+/// the review estimate's weights, and profiles, which are hashed as the
+/// resolved settings instead.
+/// `[[project]]` is hashed as the dependency list and `[baseline]` as the
+/// baseline's content.
+const UNHASHED_SECTIONS: &[&str] = &[
+    "cache",
+    "storage",
+    "review_estimate",
+    "profiles",
+    "project",
+    "baseline",
+];
+
+/// Modules that read git history rather than the files alone
+const HISTORY_MODULES: &[&str] = &["hotspots", "ownership"];
+
+/// A monorepo project and the projects it depends on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    /// Directory relative to the repository root, `/`-separated
+    pub path: String,
+    /// Directories of the projects it depends on, transitively, sorted
+    pub depends_on: Vec<String>,
+}
+
+impl Project {
+    /// The project at `dir`, relative to `repo_root` or absolute inside it
+    pub fn resolve(repo_root: &Path, dir: &Path, config: &RevetConfig) -> Result<Self> {
+        let relative = if dir.is_absolute() {
+            let dir = std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+            match dir.strip_prefix(repo_root) {
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) => bail!(
+                    "--project {} is outside the repository {}",
+                    dir.display(),
+                    repo_root.display()
+                ),
+            }
+        } else {
+            dir.to_path_buf()
+        };
+        if relative
+            .components()
+            .any(|c| matches!(c, Component::ParentDir))
+        {
+            bail!(
+                "--project {} must be inside the repository",
+                relative.display()
+            );
+        }
+        let path = normalize(&relative);
+        if path.is_empty() {
+            bail!("--project must name a directory below the repository root");
+        }
+        if !repo_root.join(&path).is_dir() {
+            bail!("--project {}: no such directory", path);
+        }
+        let declared = config
+            .projects
+            .iter()
+            .any(|p| normalize_str(&p.path) == path);
+        if !declared
+            && !MANIFESTS
+                .iter()
+                .any(|m| repo_root.join(&path).join(m).is_file())
+        {
+            bail!(
+                "--project {}: not a project (no manifest such as package.json or Cargo.toml, and no [[project]] entry)",
+                path
+            );
+        }
+
+        let mut depends_on: Vec<String> = Vec::new();
+        let mut pending = vec![path.clone()];
+        while let Some(current) = pending.pop() {
+            let entries = config
+                .projects
+                .iter()
+                .filter(|p| normalize_str(&p.path) == current);
+            for dep in entries.flat_map(|p| &p.depends_on) {
+                let dep = normalize_str(dep);
+                if dep != path && !depends_on.contains(&dep) {
+                    depends_on.push(dep.clone());
+                    pending.push(dep);
+                }
+            }
+        }
+        depends_on.sort();
+        Ok(Self { path, depends_on })
+    }
+
+    /// Whether `file` (absolute, or relative to `repo_root`) is in the
+    /// project's directory
+    pub fn contains(&self, repo_root: &Path, file: &Path) -> bool {
+        within(&relative_path(repo_root, file), &self.path)
+    }
+
+    /// Directory of the project `file` (relative to `repo_root`) belongs
+    /// to: the deepest `[[project]]` or manifest directory holding it, or
+    /// `""` for the repository root
+    pub fn of(repo_root: &Path, file: &Path, config: &RevetConfig) -> String {
+        let relative = normalize(file.strip_prefix(repo_root).unwrap_or(file));
+        let mut dir = Path::new(&relative).parent();
+        while let Some(current) = dir.filter(|d| !d.as_os_str().is_empty()) {
+            let path = normalize(current);
+            let declared = config
+                .projects
+                .iter()
+                .any(|p| normalize_str(&p.path) == path);
+            if declared
+                || MANIFESTS
+                    .iter()
+                    .any(|m| repo_root.join(&path).join(m).is_file())
+            {
+                return path;
+            }
+            dir = current.parent();
+        }
+        String::new()
+    }
+
+    /// Whether `file` is in the project or a project it depends on
+    pub fn covers(&self, repo_root: &Path, file: &Path) -> bool {
+        let relative = relative_path(repo_root, file);
+        within(&relative, &self.path) || self.depends_on.iter().any(|d| within(&relative, d))
+    }
+}
+
+/// Everything a project's findings depend on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HashInputs {
+    pub format: u32,
+    pub project: String,
+    pub depends_on: Vec<String>,
+    /// Relative `/`-separated path → SHA-256 of the content
+    pub files: BTreeMap<String, String>,
+    /// Resolved profile settings and configuration
+    pub config: serde_json::Value,
+    /// `[modules]` name → version; built-in analyzers are versioned by
+    /// the revet version
+    pub analyzers: BTreeMap<String, String>,
+    /// SHA-256 of the baseline; `None` without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<String>,
+    /// `HEAD` commit, when a module reads git history
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<String>,
+}
+
+impl HashInputs {
+    /// Collect the inputs of `project` from the files a full scan of the
+    /// repository discovered, the configuration `settings` resolved to,
+    /// the modules that run and the baseline file, if any
+    pub fn collect(
+        repo_root: &Path,
+        project: &Project,
+        files: &[PathBuf],
+        config: &RevetConfig,
+        settings: &ProfileSettings,
+        modules: &[&str],
+        baseline: Option<&Path>,
+    ) -> Result<Self> {
+        let mut hashed = BTreeMap::new();
+        for file in files.iter().filter(|f| project.covers(repo_root, f)) {
+            let bytes = std::fs::read(repo_root.join(file))
+                .with_context(|| format!("failed to read {}", file.display()))?;
+            hashed.insert(relative_path(repo_root, file), content_hash(&bytes));
+        }
+
+        let baseline = match baseline.filter(|p| p.is_file()) {
+            Some(path) => Some(content_hash(&std::fs::read(path).with_context(|| {
+                format!("failed to read the baseline {}", path.display())
+            })?)),
+            None => None,
+        };
+
+        let reads_history =
+            settings.history_secrets || modules.iter().any(|m| HISTORY_MODULES.contains(m));
+        let history = if reads_history {
+            crate::GraphCache::get_git_commit_hash(repo_root)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            format: FORMAT_VERSION,
+            project: project.path.clone(),
+            depends_on: project.depends_on.clone(),
+            files: hashed,
+            config: config_slice(config, settings)?,
+            analyzers: modules
+                .iter()
+                .map(|m| (m.to_string(), crate::VERSION.to_string()))
+                .collect(),
+            baseline,
+            history,
+        })
+    }
+
+    /// SHA-256 of the inputs, as lowercase hex
+    pub fn digest(&self) -> String {
+        // Through `Value`, whose maps are sorted, so the bytes are stable
+        let value = serde_json::to_value(self).expect("hash inputs serialize");
+        hex(&Sha256::digest(value.to_string().as_bytes()))
+    }
+}
+
+/// The resolved settings and the configuration sections that can change
+/// findings
+pub fn config_slice(config: &RevetConfig, settings: &ProfileSettings) -> Result<serde_json::Value> {
+    let mut config = serde_json::to_value(config).context("failed to serialize the config")?;
+    if let Some(sections) = config.as_object_mut() {
+        for section in UNHASHED_SECTIONS {
+            sections.remove(*section);
+        }
+        if !settings.ai {
+            sections.remove("ai");
+        }
+    }
+    let mut settings = serde_json::to_value(settings).context("failed to serialize the profile")?;
+    // Which flags set them doesn't matter, only what they resolved to
+    if let Some(settings) = settings.as_object_mut() {
+        settings.remove("overrides");
+    }
+    Ok(serde_json::json!({ "settings": settings, "config": config }))
+}
+
+/// SHA-256 of `bytes` with `\r\n` line endings read as `\n`
+pub fn content_hash(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    let mut rest = bytes;
+    while let Some(cr) = rest.windows(2).position(|w| w == b"\r\n") {
+        hasher.update(&rest[..cr]);
+        rest = &rest[cr + 1..];
+    }
+    hasher.update(rest);
+    hex(&hasher.finalize())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// `file` relative to `repo_root`, `/`-separated
+fn relative_path(repo_root: &Path, file: &Path) -> String {
+    normalize(file.strip_prefix(repo_root).unwrap_or(file))
+}
+
+fn normalize(path: &Path) -> String {
+    path.components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn normalize_str(path: &str) -> String {
+    normalize(Path::new(&path.replace('\\', "/")))
+}
+
+fn within(relative: &str, dir: &str) -> bool {
+    relative
+        .strip_prefix(dir)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
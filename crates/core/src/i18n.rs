@@ -0,0 +1,447 @@
+//! Message catalogs — localizable finding messages and suggestions
+//!
+//! Analyzers describe a finding with a [`Msg`]: a catalog key plus named
+//! parameters, built with the [`msg!`](crate::msg) macro. The English text
+//! stored in [`Finding::message`](crate::Finding::message) is rendered from the
+//! `en` catalog; output formats render the same `Msg` again in the locale the
+//! user selected (`[output] locale` or `REVET_LOCALE`).
+//!
+//! Catalogs are embedded Fluent files using a small subset of the syntax:
+//!
+//! ```text
+//! # comment
+//! error-handling-unwrap = .unwrap() call
+//!     .suggestion = Use ? operator or .expect() with a descriptive message
+//! secrets-detected = Possible { $kind } detected ({ $value })
+//! ```
+//!
+//! Messages are `id = text`, attributes are indented `.name = text` lines
+//! (referenced as `id.name`), and placeables are `{ $param }` or string
+//! literals such as `{ "{" }`. Indented lines that are not attributes continue
+//! the previous value on a new line. Keys missing from a catalog fall back to
+//! English, and keys missing from English render as the key itself.
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Environment variable that overrides `[output] locale`
+pub const LOCALE_ENV: &str = "REVET_LOCALE";
+
+/// Language findings are rendered in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    En,
+    Ja,
+}
+
+impl Locale {
+    /// Every locale with an embedded catalog
+    pub const ALL: &'static [Locale] = &[Locale::En, Locale::Ja];
+
+    /// Parse a locale tag, ignoring region and encoding (`ja`, `ja-JP`,
+    /// `ja_JP.UTF-8`). Returns `None` for languages without a catalog.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let language = tag
+            .split(['-', '_', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "en" | "c" | "posix" => Some(Locale::En),
+            "ja" => Some(Locale::Ja),
+            _ => None,
+        }
+    }
+
+    /// Resolve the locale for a run: `REVET_LOCALE` wins over the configured
+    /// value; unknown or empty values fall back to English.
+    pub fn resolve(configured: &str) -> Self {
+        let env = std::env::var(LOCALE_ENV).ok();
+        Self::resolve_from(env.as_deref(), configured)
+    }
+
+    /// [`resolve`](Self::resolve) with the environment value passed in
+    pub fn resolve_from(env: Option<&str>, configured: &str) -> Self {
+        env.filter(|v| !v.trim().is_empty())
+            .and_then(Self::parse)
+            .or_else(|| Self::parse(configured))
+            .unwrap_or_default()
+    }
+
+    /// Short language code (`"en"`, `"ja"`)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Ja => "ja",
+        }
+    }
+
+    /// The embedded catalog for this locale
+    pub fn catalog(&self) -> &'static Catalog {
+        static EN: OnceLock<Catalog> = OnceLock::new();
+        static JA: OnceLock<Catalog> = OnceLock::new();
+        match self {
+            Locale::En => EN.get_or_init(|| Catalog::parse(include_str!("i18n/en.ftl")).unwrap()),
+            Locale::Ja => JA.get_or_init(|| Catalog::parse(include_str!("i18n/ja.ftl")).unwrap()),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// One piece of a catalog entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Param(String),
+}
+
+/// A parsed catalog: `id` and `id.attr` keys to message patterns
+#[derive(Debug, Default)]
+pub struct Catalog {
+    entries: HashMap<String, Vec<Part>>,
+}
+
+impl Catalog {
+    /// Parse a catalog in the Fluent subset described in the module docs
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut entries: HashMap<String, String> = HashMap::new();
+        let mut order: Vec<String> = Vec::new();
+        let mut message: Option<String> = None;
+        let mut current: Option<String> = None;
+
+        for (i, raw) in source.lines().enumerate() {
+            let line_no = i + 1;
+            if raw.trim().is_empty() {
+                continue;
+            }
+            if raw.starts_with('#') {
+                current = None;
+                continue;
+            }
+
+            if raw.starts_with([' ', '\t']) {
+                let trimmed = raw.trim();
+                if let Some(attr) = trimmed.strip_prefix('.') {
+                    let Some(id) = &message else {
+                        bail!("line {}: attribute outside a message", line_no);
+                    };
+                    let (name, value) = split_entry(attr)
+                        .ok_or_else(|| anyhow!("line {}: expected `.name = value`", line_no))?;
+                    let key = format!("{}.{}", id, name);
+                    if entries.insert(key.clone(), value.to_string()).is_some() {
+                        bail!("line {}: duplicate key `{}`", line_no, key);
+                    }
+                    order.push(key.clone());
+                    current = Some(key);
+                } else {
+                    let Some(key) = &current else {
+                        bail!("line {}: unexpected indented line", line_no);
+                    };
+                    let value = entries.get_mut(key).expect("current key is inserted");
+                    if !value.is_empty() {
+                        value.push('\n');
+                    }
+                    value.push_str(trimmed);
+                }
+                continue;
+            }
+
+            let (id, value) = split_entry(raw)
+                .ok_or_else(|| anyhow!("line {}: expected `id = value`", line_no))?;
+            if entries.insert(id.to_string(), value.to_string()).is_some() {
+                bail!("line {}: duplicate key `{}`", line_no, id);
+            }
+            order.push(id.to_string());
+            message = Some(id.to_string());
+            current = Some(id.to_string());
+        }
+
+        let mut parsed = HashMap::with_capacity(entries.len());
+        for key in order {
+            let value = &entries[&key];
+            let parts = parse_pattern(value).map_err(|e| anyhow!("`{}`: {}", key, e))?;
+            parsed.insert(key, parts);
+        }
+        Ok(Self { entries: parsed })
+    }
+
+    /// Whether `key` (`id` or `id.attr`) has an entry
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// All keys, sorted
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = self.entries.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// Parameter names referenced by `key`, sorted and deduplicated
+    pub fn params(&self, key: &str) -> Vec<&str> {
+        let mut params: Vec<&str> = self
+            .entries
+            .get(key)
+            .into_iter()
+            .flatten()
+            .filter_map(|part| match part {
+                Part::Param(name) => Some(name.as_str()),
+                Part::Text(_) => None,
+            })
+            .collect();
+        params.sort_unstable();
+        params.dedup();
+        params
+    }
+
+    /// Render `key` with `args`; `None` when the key is missing.
+    /// Parameters without an argument render as `{$name}`.
+    pub fn format(&self, key: &str, args: &[(&str, String)]) -> Option<String> {
+        let parts = self.entries.get(key)?;
+        let mut out = String::new();
+        for part in parts {
+            match part {
+                Part::Text(text) => out.push_str(text),
+                Part::Param(name) => match args.iter().find(|(n, _)| n == name) {
+                    Some((_, value)) => out.push_str(value),
+                    None => {
+                        out.push_str("{$");
+                        out.push_str(name);
+                        out.push('}');
+                    }
+                },
+            }
+        }
+        Some(out)
+    }
+}
+
+/// Split `id = value`, checking the id is a Fluent identifier
+fn split_entry(line: &str) -> Option<(&str, &str)> {
+    let (id, value) = line.split_once('=')?;
+    let id = id.trim();
+    let mut chars = id.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then_some((id, value.trim()))
+}
+
+/// Split a message value into text and `{ $param }` / `{ "literal" }` parts
+fn parse_pattern(value: &str) -> Result<Vec<Part>> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut rest = value;
+
+    while let Some(open) = rest.find(['{', '}']) {
+        if rest[open..].starts_with('}') {
+            bail!("unbalanced `}}`");
+        }
+        text.push_str(&rest[..open]);
+        let after = rest[open + 1..].trim_start();
+        if let Some(quoted) = after.strip_prefix('"') {
+            let end = quoted
+                .find('"')
+                .ok_or_else(|| anyhow!("unterminated string literal"))?;
+            text.push_str(&quoted[..end]);
+            rest = quoted[end + 1..]
+                .trim_start()
+                .strip_prefix('}')
+                .ok_or_else(|| anyhow!("unterminated placeable"))?;
+            continue;
+        }
+        let close = after
+            .find('}')
+            .ok_or_else(|| anyhow!("unterminated placeable"))?;
+        let inner = after[..close].trim();
+        let name = inner
+            .strip_prefix('$')
+            .filter(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+            .ok_or_else(|| anyhow!("unsupported placeable `{{{}}}`", inner))?;
+        if !text.is_empty() {
+            parts.push(Part::Text(std::mem::take(&mut text)));
+        }
+        parts.push(Part::Param(name.to_string()));
+        rest = &after[close + 1..];
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+    Ok(parts)
+}
+
+/// A catalog reference: message key plus named parameters
+///
+/// Parameters hold data that is never translated — file names, identifiers,
+/// code, counts — or another catalog entry rendered in the same locale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Msg {
+    key: &'static str,
+    args: Vec<(&'static str, Arg)>,
+}
+
+/// A [`Msg`] parameter value
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Arg {
+    Text(String),
+    Msg(Msg),
+}
+
+impl Msg {
+    pub fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            args: Vec::new(),
+        }
+    }
+
+    /// Set a parameter, replacing any earlier value for `name`
+    pub fn arg(self, name: &'static str, value: impl fmt::Display) -> Self {
+        self.set(name, Arg::Text(value.to_string()))
+    }
+
+    /// Set a parameter to another catalog entry, rendered in the same locale
+    pub fn arg_msg(self, name: &'static str, value: Msg) -> Self {
+        self.set(name, Arg::Msg(value))
+    }
+
+    fn set(mut self, name: &'static str, value: Arg) -> Self {
+        match self.args.iter_mut().find(|(n, _)| *n == name) {
+            Some(slot) => slot.1 = value,
+            None => self.args.push((name, value)),
+        }
+        self
+    }
+
+    /// The same parameters under a different key
+    pub fn with_key(mut self, key: &'static str) -> Self {
+        self.key = key;
+        self
+    }
+
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    /// Value of text parameter `name`, if set
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.args.iter().find_map(|(n, v)| match v {
+            Arg::Text(text) if *n == name => Some(text.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Render in `locale`, falling back to English, then to the key
+    pub fn render(&self, locale: Locale) -> String {
+        let args: Vec<(&str, String)> = self
+            .args
+            .iter()
+            .map(|(name, value)| match value {
+                Arg::Text(text) => (*name, text.clone()),
+                Arg::Msg(msg) => (*name, msg.render(locale)),
+            })
+            .collect();
+        locale
+            .catalog()
+            .format(self.key, &args)
+            .or_else(|| Locale::En.catalog().format(self.key, &args))
+            .unwrap_or_else(|| self.key.to_string())
+    }
+
+    /// Render in English — the text stored in [`Finding::message`](crate::Finding::message)
+    pub fn english(&self) -> String {
+        self.render(Locale::En)
+    }
+}
+
+/// [`Msg`] with owned strings, as it is serialized
+#[derive(Serialize, Deserialize)]
+struct StoredMsg {
+    key: String,
+    args: Vec<(String, StoredArg)>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum StoredArg {
+    Text(String),
+    Msg(StoredMsg),
+}
+
+impl From<&Msg> for StoredMsg {
+    fn from(msg: &Msg) -> Self {
+        Self {
+            key: msg.key.to_string(),
+            args: msg
+                .args
+                .iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        Arg::Text(text) => StoredArg::Text(text.clone()),
+                        Arg::Msg(msg) => StoredArg::Msg(msg.into()),
+                    };
+                    (name.to_string(), value)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl StoredMsg {
+    /// Back to a [`Msg`], with the key and parameter names taken from the
+    /// English catalog. `None` for a key it lacks; parameters no catalog
+    /// references are dropped, as they never render.
+    fn into_msg(self) -> Option<Msg> {
+        let catalog = Locale::En.catalog();
+        let key = catalog.entries.get_key_value(self.key.as_str())?.0.as_str();
+        let mut msg = Msg::new(key);
+        for (name, value) in self.args {
+            let Some(name) = Locale::ALL
+                .iter()
+                .find_map(|l| l.catalog().params(key).into_iter().find(|p| *p == name))
+            else {
+                continue;
+            };
+            msg = match value {
+                StoredArg::Text(text) => msg.set(name, Arg::Text(text)),
+                StoredArg::Msg(inner) => msg.set(name, Arg::Msg(inner.into_msg()?)),
+            };
+        }
+        Some(msg)
+    }
+}
+
+/// Serialized as its key and parameters, for caches of findings
+impl Serialize for Msg {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        StoredMsg::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Msg {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let stored = StoredMsg::deserialize(deserializer)?;
+        let key = stored.key.clone();
+        stored
+            .into_msg()
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown catalog key `{}`", key)))
+    }
+}
+
+/// Build a [`Msg`] from a catalog key and named parameters:
+/// `msg!("dead-imports-unused", name = local_name)`
+#[macro_export]
+macro_rules! msg {
+    ($key:literal $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::Msg::new($key)$(.arg(stringify!($name), $value))*
+    };
+}
@@ -0,0 +1,360 @@
+# English message catalog — the fallback for every other locale.
+#
+# Keys are `<module>-<message>`; suggestions are the `.suggestion` attribute
+# of the message they belong to. Parameters carry untranslated data (names,
+# paths, code, counts).
+
+# Shared
+platform-variants-note = { $finding } [platform variants: { $platforms }]
+
+# Complexity (CMPLX)
+complexity-length-error = Function `{ $name }` is { $lines } lines long (max recommended: { $max })
+    .suggestion = Break this function into smaller, focused functions
+complexity-length-warning = Function `{ $name }` is { $lines } lines long (recommended: <{ $max })
+    .suggestion = Consider breaking this function into smaller, focused functions
+complexity-parameters-error = Function `{ $name }` has { $count } parameters (max recommended: { $max })
+    .suggestion = Group related parameters into a struct or configuration object
+complexity-parameters-warning = Function `{ $name }` has { $count } parameters (recommended: <{ $max })
+    .suggestion = Consider grouping related parameters into a struct or object
+complexity-cyclomatic-error = Function `{ $name }` has cyclomatic complexity of { $score } (max recommended: { $max })
+    .suggestion = Reduce branching by extracting helper functions or simplifying logic
+complexity-cyclomatic-warning = Function `{ $name }` has cyclomatic complexity of { $score } (recommended: <{ $max })
+    .suggestion = Consider reducing branching by extracting helper functions
+complexity-nesting-error = Function `{ $name }` has nesting depth of { $depth } (max recommended: { $max })
+    .suggestion = Reduce nesting using early returns or helper functions
+complexity-nesting-warning = Function `{ $name }` has nesting depth of { $depth } (recommended: <{ $max })
+    .suggestion = Consider reducing nesting using early returns or helper functions
+complexity-length-delta = Function `{ $name }` grew longer in this change: { $from } → { $to } lines (threshold { $max })
+    .suggestion = Move the added code into a separate function instead of growing this one
+complexity-parameters-delta = Function `{ $name }` gained parameters in this change: { $from } → { $to } (threshold { $max })
+    .suggestion = Pass the new values in an existing struct or configuration object
+complexity-cyclomatic-delta = Function `{ $name }` got more complex in this change: cyclomatic complexity { $from } → { $to } (threshold { $max })
+    .suggestion = Extract the new branches into a helper function so this one gets no more complex
+complexity-nesting-delta = Function `{ $name }` got more deeply nested in this change: depth { $from } → { $to } (threshold { $max })
+    .suggestion = Use early returns or a helper function for the new code instead of nesting deeper
+
+# Coupling (COUPLE)
+coupling-envy = Function `{ $name }` makes { $foreign } references into `{ $file }` and { $home } into its own file
+    .suggestion = Move the function next to the code it uses in `{ $file }`, or move that logic into `{ $file }` behind one call
+coupling-intimacy = `{ $file }` and `{ $other }` reference each other heavily: { $forward } edges one way and { $backward } back across { $symbols } symbols (density { $density })
+    .suggestion = Merge the two files, or move what they share into one of them or a module both depend on
+
+# Concurrency (RACE)
+concurrency-shared-state = `{ $var }` is mutated without a lock in `{ $function }`, which runs concurrently from `{ $entry }`
+    .suggestion = Guard the mutation with a lock (`with lock:`), or give each thread its own state and combine the results
+concurrency-handler-state = Module-level `{ $var }` is written in `{ $function }`, which runs for concurrent requests from `{ $entry }`
+    .suggestion = Keep per-request state in the request, and shared state in a store made for concurrent access
+concurrency-static-mut = `static mut { $name }` can be read and written from any thread without synchronization
+    .suggestion = Use an atomic, a `Mutex` or a `OnceLock` instead
+    .no-oncelock = Use an atomic, or a `Mutex` in a lazily initialized static (`once_cell`, `lazy_static`); `OnceLock` needs Rust 1.70
+concurrency-unsafe-send-sync = `unsafe impl { $trait_name } for { $type_name }` asserts thread safety the compiler can't check
+    .suggestion = Make the fields thread-safe so the compiler derives it, or document why sharing the type across threads is sound
+
+# Documentation (DOC)
+docs-undocumented = Public symbol `{ $name }` has no documentation comment
+    .suggestion = Add a doc comment saying what it does and how to use it
+docs-coverage = Documentation coverage of `{ $project }` is { $percent }% ({ $documented } of { $public } public symbols), below the minimum of { $min }%
+    .suggestion = Document the public symbols listed as `DOC` findings, or lower `[analyzers.docs] min_coverage`
+
+# Hotspots (HOT)
+hotspots-file = Hotspot: changed { $changes } times in { $days } days, aggregate complexity { $complexity }
+    .suggestion = Prioritize refactoring this file: split it up or simplify its most complex functions
+
+# Ownership (OWN)
+ownership-orphaned-months = Orphaned: { $departed }% of lines last touched by departed contributors ({ $owners }), last change { $months } months ago
+ownership-orphaned-days = Orphaned: { $departed }% of lines last touched by departed contributors ({ $owners }), last change { $days } days ago
+    .suggestion = Assign an owner in CODEOWNERS, or have an active maintainer review this code before it needs changing
+
+# Slow files (PERF)
+performance-slow-file = Slow to analyze: { $analyzer } took { $ms } ms on this file (threshold { $max } ms)
+    .suggestion = Exclude the file if it is generated or vendored, otherwise split it up or report the slowdown
+
+# Translations (I18N)
+i18n-missing-key = Translation key `{ $key }` is missing from { $locales }
+    .suggestion = Add `{ $key }` to the { $locales } catalogs
+i18n-unused-key = Translation key `{ $key }` is never looked up ({ $locales })
+    .suggestion = Remove `{ $key }` from the catalogs if no code builds it at runtime
+i18n-missing-namespace = Locale { $locale } has no `{ $namespace }` namespace (defined in { $default })
+    .suggestion = Add the `{ $namespace }` catalog for { $locale }
+i18n-dynamic-key = Translation key `{ $expr }` is built at runtime and cannot be checked against the catalogs
+    .suggestion = Look keys up by literal, e.g. through a map from values to keys, so missing translations are caught
+
+# Feature flags (FLAG)
+flags-stale-launched = Flag `{ $flag }` is launched; remove the check and the dead branch
+    .suggestion = Keep the code the flag turns on and delete the check and its fallback
+flags-stale-launched-since = Flag `{ $flag }` was launched { $since }; remove the check and the dead branch
+    .suggestion = Keep the code the flag turns on and delete the check and its fallback
+flags-stale-retired = Flag `{ $flag }` is retired; remove the check and the code it guards
+    .suggestion = Keep the fallback and delete the check and the code that only ran with the flag on
+flags-stale-retired-since = Flag `{ $flag }` was retired { $since }; remove the check and the code it guards
+    .suggestion = Keep the fallback and delete the check and the code that only ran with the flag on
+flags-dead-branch-launched = Lines { $start }–{ $end } only run while launched flag `{ $flag }` is off
+    .suggestion = Delete these lines together with the check on line { $line }
+flags-dead-branch-retired = Lines { $start }–{ $end } only run while retired flag `{ $flag }` is on
+    .suggestion = Delete these lines together with the check on line { $line }
+flags-unknown = Flag `{ $flag }` is not in the flag inventory ({ $inventory })
+    .suggestion = Add the flag to the inventory with its state and owner, or fix the name if it is a typo
+flags-unused = Flag `{ $flag }` is in the inventory but no code checks it
+    .suggestion = Mark the flag retired once it is archived in the flag system, or remove the entry
+flags-dynamic = Flag name `{ $expr }` is built at runtime and cannot be checked against the inventory
+    .suggestion = Pass flag names as string literals so stale and unknown flags are caught
+flags-invalid-inventory = Flag inventory cannot be read: { $error }
+    .suggestion = Fix the entry; each [[flag]] needs a unique name and a state of active, launched or retired
+
+# Refactor verification (REF)
+refactor-arity-mismatch = Call to `{ $name }` passes { $count } argument(s), but this change made its signature `{ $new }` (was `{ $old }`)
+    .suggestion = Update this call, which the change to { $file } left as it was, or give the new parameters defaults
+refactor-stale-reference = `{ $old }` no longer exists: this change renamed it to `{ $new }` in { $file }
+    .suggestion = Use `{ $new }` here, or keep `{ $old }` as an alias until every caller is updated
+
+# Debug artifacts (DBG)
+debug-debugger = Debugger statement `{ $call }` left in the code
+    .suggestion = Remove it; it stops the program wherever this line runs
+debug-print = `{ $call }` looks like leftover print debugging
+    .suggestion = Remove it, or log through the project's logger with a message that says what happened
+debug-commented-code = { $lines } lines of commented-out code (lines { $start }–{ $end })
+    .suggestion = Delete the block; version control keeps the old code
+
+# Test quality (TEST)
+test-sleep = Fixed wait `{ $call }` in test code
+    .suggestion = Wait for the condition itself (poll with a timeout, use a fake clock or an event) instead of sleeping
+test-no-assertions = Test `{ $test }` asserts nothing
+    .suggestion = Check the outcome, or list the helper that asserts in [analyzers.test_quality] assertion_patterns
+test-stale-skip = Test disabled by `{ $marker }` for { $days } days
+    .suggestion = Fix and re-enable the test, or delete it
+test-broad-exception = `{ $assertion }` passes for any exception
+    .suggestion = Expect the specific exception type the code raises, and its message where it matters
+
+# Stale copy (COPY)
+copy-copyright-year = Copyright notice ends in { $year }, but it is { $current }
+    .suggestion = Update the year to { $current }, or fill it in from the current date when the page is built
+copy-version-reference = Copy mentions version { $version }, { $behind } major version(s) behind { $current }
+    .suggestion = Update or drop the reference; "new in" notes go stale with every release
+copy-banned-term = `{ $term }` is a retired name
+    .suggestion = Say `{ $replacement }` instead
+    .reword = Reword the text without `{ $term }`
+
+# Numeric precision (NUM)
+numeric-money-float = `{ $name }` holds money in a binary floating-point number
+    .python = Use `decimal.Decimal` (built from strings, not floats), or an `int` count of cents
+    .javascript = Use an integer count of cents, or a decimal library such as decimal.js or big.js
+    .java = Use `BigDecimal` (built from strings, not doubles), or a `long` count of cents
+    .go = Use an `int64` count of cents, or a decimal package such as github.com/shopspring/decimal
+    .rust = Use an `i64` count of cents, or a decimal type such as `rust_decimal::Decimal`
+    .csharp = Use `decimal`
+numeric-float-equality = `{ $expression }` compares floating-point numbers exactly
+    .suggestion = Compare within a tolerance (`math.isclose`, `Math.abs(a - b) < epsilon`), or keep the values in integers or decimals
+numeric-unsafe-id-literal = ID `{ $name }` is set to { $literal }, more digits than a JavaScript number keeps
+    .suggestion = Write the ID as a string, or as a BigInt literal (`{ $literal }n`)
+    .no-bigint = Write the ID as a string; BigInt literals need an ES2020 target
+numeric-unsafe-id-parsed = ID `{ $name }` comes from `JSON.parse`, which rounds integers beyond 2^53
+    .suggestion = Have the API send IDs as strings, or parse with a reviver or a library such as json-bigint
+
+# Pagination (PAGE)
+pagination-unbounded = `{ $handler }` returns every result of `{ $query }` without pagination
+    .prisma = Pass `take` with `skip` or `cursor` to `findMany`, read from `limit`/`cursor` query parameters
+    .fastify = Read `limit` and `cursor` from `request.query` and bound the query, or use a Fastify pagination plugin
+    .django = Set a DRF pagination class (`PageNumberPagination`, `LimitOffsetPagination`, `CursorPagination`), or slice the queryset
+    .sqlalchemy = Take `limit` and `offset` (or a cursor) parameters and apply `.limit()`/`.offset()` to the query
+    .sequelize = Pass `limit` and `offset` to `findAll`, or use `findAndCountAll` with them
+    .mongo = Chain `.limit()` and `.skip()` onto `find`, or filter on a cursor
+    .sql = Add `LIMIT` and `OFFSET`, or keyset pagination on an indexed column, from request parameters
+
+# Accessibility (A11Y)
+a11y-img-alt = `<{ $tag }>` has no alt text
+    .suggestion = Add `alt="…"` describing the image, or `alt=""` if it is purely decorative
+a11y-click-events = `<{ $tag }>` handles clicks but has no keyboard handler or role
+    .suggestion = Use a `<button>`, or add `{ $attributes }` so keyboard and screen-reader users can activate it
+a11y-label = Form field `<{ $tag }>` has no associated label
+    .suggestion = Wrap it in a `<label>`, point `{ $label }` at it, or add `aria-label="…"`
+a11y-html-lang = `<html>` has no lang attribute
+    .suggestion = Add `lang="…"` with the page's language, such as `lang="en"`
+a11y-tabindex = `{ $attribute }` on `<{ $tag }>` moves it ahead of the page's natural tab order
+    .suggestion = Use `{ $fix }` and order the markup the way focus should move
+
+# Web security configuration (WEBSEC)
+websec-cors-wildcard-credentials = { $pattern }: CORS allows any origin ({ $origin }) together with credentials
+    .suggestion = List the trusted origins instead (`{ $setting }`); with credentials, any site could make authenticated requests and read the responses
+    .runtime = { $pattern }: CORS allows credentials, and whether it allows every origin depends on `{ $value }`; verify at runtime that only trusted origins get credentials
+websec-cookie-httponly = { $pattern }: cookie `{ $cookie }` is set without HttpOnly
+    .suggestion = Set `{ $setting }` so scripts, and any injected one, cannot read the cookie
+    .runtime = { $pattern }: HttpOnly of cookie `{ $cookie }` comes from `{ $value }`; verify at runtime that it is on
+websec-cookie-secure = { $pattern }: cookie `{ $cookie }` is set without Secure
+    .suggestion = Set `{ $setting }` so the cookie is never sent over plain HTTP
+    .runtime = { $pattern }: Secure of cookie `{ $cookie }` comes from `{ $value }`; verify at runtime that it is on
+websec-cookie-samesite = { $pattern }: cookie `{ $cookie }` is set without SameSite
+    .suggestion = Set `{ $setting }` so cross-site requests don't carry the cookie
+    .runtime = { $pattern }: SameSite of cookie `{ $cookie }` comes from `{ $value }`; verify at runtime that it is set
+websec-headers-disabled = { $pattern }: `{ $header }` is turned off
+    .suggestion = Use `{ $setting }`, and turn off only the single header that gets in the way, with a comment saying why
+    .remove = Remove the call turning off `{ $header }`, and turn off only the single header that gets in the way, with a comment saying why
+    .runtime = { $pattern }: `{ $header }` is set from `{ $value }`; verify at runtime that it stays on
+websec-headers-missing = { $pattern }: no security-header middleware ({ $middleware }) is applied in this file
+    .suggestion = Add `{ $setting }`, or suppress the finding if a proxy or another module sets the headers
+
+# API contract (CONTRACT)
+contract-unmatched-call = `{ $route }` ({ $callee }) matches no backend endpoint
+    .method = `{ $route }` ({ $callee }) matches no backend endpoint; the path is served for { $methods }
+    .closest = Did you mean `{ $endpoint }` ({ $location })? Fix the path or method, or add the endpoint
+    .suggestion = Fix the path or method, or add the endpoint; suppress the finding for calls to another service
+contract-uncalled-endpoint = `{ $route }` ({ $handler }) has no caller in this repository
+    .suggestion = Remove the endpoint if nothing outside the repository calls it, or turn off `contract:uncalled_endpoint` for a public API
+contract-field-mismatch = `{ $route }` is sent fields { $model } does not accept ({ $extra }) and lacks required ones ({ $missing })
+    .extra = `{ $route }` is sent fields { $model } does not accept: { $extra }
+    .missing = `{ $route }` is sent a body lacking fields { $model } requires: { $missing }
+    .suggestion = Align the request body with { $model } ({ $location })
+contract-dynamic-url = `{ $callee }` builds its URL at runtime (`{ $expr }`); it cannot be matched to an endpoint
+    .suggestion = Use a literal path or a template literal so the call can be checked
+
+# Repository (REPO)
+repository-license = No LICENSE file at the repository root
+    .suggestion = Add a LICENSE (or COPYING) file stating the terms the code may be used under
+repository-stale-baseline = The baseline holds { $entries } findings and was created { $days } days ago
+    .suggestion = Fix or expire baselined findings, then re-create the baseline with `revet baseline`
+repository-no-security = `[modules] security` is off, so no security analyzer runs
+    .suggestion = Turn `security` back on and suppress individual findings instead
+repository-codeowners = `{ $branch }` is declared protected, but the repository has no CODEOWNERS file
+    .suggestion = Add CODEOWNERS at the root, in `.github/` or in `docs/` so changes to `{ $branch }` have reviewers
+repository-required-check = Required check `{ $check }` is not a job of any workflow in `.github/workflows`
+    .suggestion = Add a job with that id or `name`, or update `required_checks` in `[analyzers.repository]`
+
+# Upgrade advisor (UPGRADE)
+upgrade-removed = `{ $api }` was removed in { $runtime } { $version } (target { $target })
+upgrade-changed = `{ $api }` behaves differently since { $runtime } { $version } (target { $target })
+upgrade-changed-note = `{ $api }` changed in { $runtime } { $version } (target { $target }): it { $note }
+upgrade-deprecated = `{ $api }` is deprecated since { $runtime } { $version } (target { $target })
+upgrade-deprecated-removal = `{ $api }` is deprecated since { $runtime } { $version } and removed in { $removal } (target { $target })
+upgrade-use-instead = Use { $replacement } instead
+upgrade-remove-use = Stop using `{ $api }` before upgrading
+upgrade-invalid-pack = Upgrade pack cannot be loaded: { $error }
+    .suggestion = Fix the pack, or remove it from `[upgrade] packs`; each [[api]] needs a module and a deprecated, changed or removed version
+
+# Import boundaries (BOUNDARY)
+boundary-test-import = Production code imports test code: `{ $module }`
+    .suggestion = Move the shared code out of the test tree, or only import it from tests
+boundary-dev-dependency = Production code imports `{ $module }`, a development-only dependency
+    .suggestion = Move the package to the runtime dependencies, or keep the import out of production code
+
+# Circular imports (CYCLE)
+circular-imports-cycle = Circular import detected: { $cycle }
+    .suggestion = Break the cycle by extracting shared code to a separate module
+
+# Dead imports (DIMPORT)
+dead-imports-unused = `{ $name }` is imported but never used
+    .suggestion = Remove the unused import of `{ $name }`
+
+# Error handling (ERR)
+error-handling-empty-catch = Empty catch/except block
+    .suggestion = Handle the error or add a comment explaining why it is safe to ignore
+error-handling-bare-except = Bare except without exception type
+    .suggestion = Specify an exception type: except ValueError: or except Exception as e:
+error-handling-unwrap = .unwrap() call
+    .suggestion = Use ? operator, .unwrap_or(), .unwrap_or_else(), or .expect() with a descriptive message
+error-handling-panic = panic!/todo!/unimplemented! in non-test code
+    .suggestion = Return a Result with a descriptive error instead of panicking
+error-handling-expect-message = .expect() with non-descriptive message
+    .suggestion = Use a descriptive message: .expect("Failed to open config file")
+error-handling-swallowed = Catch block only logs error
+    .suggestion = Re-throw the error or handle it properly after logging
+error-handling-broad-except = Too-broad exception catch
+    .suggestion = Catch a more specific exception type (e.g. ValueError, KeyError)
+error-handling-empty-promise-catch = Empty .catch() callback
+    .suggestion = Handle or re-throw the error in the .catch() callback
+error-handling-discarded-error = Discarded error in Go
+    .suggestion = Handle the error: if err != nil { "{" } return err { "}" }
+
+# Secret exposure (SEC, HIST)
+secrets-detected = Possible { $kind } detected ({ $value })
+secrets-in-history = Possible { $kind } detected ({ $value }) in git history (commit { $commit }, no longer in HEAD)
+    .suggestion = Rotate this credential — deleting it from HEAD does not remove it from git history
+secrets-dotenv = Possible { $kind } in { $key } ({ $value })
+secrets-dotenv-value = Secret Value
+    .suggestion = Keep real credentials out of the repository: commit a .env.example with placeholders and load the values from the environment or a secrets manager
+secrets-tracked-env = { $file } is tracked by git
+    .suggestion = Run `git rm --cached { $file }`, add it to .gitignore and rotate every credential it contains; commit a .env.example with placeholders instead
+secrets-aws-access-key-id = AWS Access Key ID
+    .suggestion = Use environment variable AWS_ACCESS_KEY_ID instead
+secrets-aws-secret-access-key = AWS Secret Access Key
+    .suggestion = Use environment variable AWS_SECRET_ACCESS_KEY instead
+secrets-github-token = GitHub Token
+    .suggestion = Use environment variable GITHUB_TOKEN instead
+secrets-private-key-pem = Private Key (PEM)
+    .suggestion = Store private key in a file outside the repo and reference via path
+secrets-database-connection-string = Database Connection String
+    .suggestion = Store connection string in .env file or use a secrets manager
+secrets-stripe-secret-restricted-key-live = Stripe Secret / Restricted Key (live)
+    .suggestion = Store Stripe keys in environment variables; never commit live keys
+secrets-slack-token = Slack Token
+    .suggestion = Store Slack tokens in environment variables or a secrets manager
+secrets-sendgrid-api-key = SendGrid API Key
+    .suggestion = Store SendGrid API key in environment variable SENDGRID_API_KEY
+secrets-twilio-auth-token = Twilio Auth Token
+    .suggestion = Store Twilio auth token in environment variable TWILIO_AUTH_TOKEN
+secrets-azure-storage-connection-string = Azure Storage Connection String
+    .suggestion = Store Azure connection string in environment variable or Azure Key Vault
+secrets-stripe-publishable-key-live = Stripe Publishable Key (live)
+    .suggestion = Even publishable keys should be stored in config, not hardcoded
+secrets-gcp-service-account-email = GCP Service Account Email
+    .suggestion = Do not embed GCP service account key JSON in source code; use Workload Identity or a secrets manager
+secrets-base64-secret = Base64-encoded secret in sensitive variable
+    .suggestion = Possible base64-encoded secret; store credentials in environment variables or a secrets manager rather than encoding them in source
+secrets-generic-api-key = Generic API Key
+    .suggestion = Store API key in environment variable or .env file
+secrets-generic-secret-key = Generic Secret Key
+    .suggestion = Store secret key in environment variable or .env file
+secrets-hardcoded-password = Hardcoded Password
+    .suggestion = Store password in environment variable or use a secrets manager
+secrets-custom = { $name }
+    .suggestion = Store this credential in environment variables or a secrets manager
+
+# Configuration drift (CONF)
+config-drift-module-disabled = This change disables the `{ $module }` analyzer
+    .suggestion = Confirm that `{ $module }` findings should no longer be reported, or keep the module on
+config-drift-module-enabled = This change enables the `{ $module }` analyzer
+config-drift-severity-lowered = `[severity] { $id }` lowered from { $from } to { $to }
+    .suggestion = Confirm that `{ $id }` findings should be reported at { $to }
+config-drift-severity-raised = `[severity] { $id }` raised from { $from } to { $to }
+config-drift-threshold-raised = `{ $key }` raised from { $from } to { $to } — fewer findings are reported
+    .suggestion = Confirm the new limit, or fix the findings that exceed the old one
+config-drift-threshold-lowered = `{ $key }` lowered from { $from } to { $to }
+config-drift-path-excluded = `{ $value }` is newly excluded from analysis
+    .suggestion = Confirm that files matching `{ $value }` should no longer be reviewed
+config-drift-path-included = `{ $value }` is no longer excluded from analysis
+config-drift-finding-ignored = `{ $value }` findings are newly ignored everywhere
+    .suggestion = Suppress individual findings with `revet-ignore` instead of ignoring `{ $value }` everywhere
+config-drift-finding-unignored = `{ $value }` findings are no longer ignored
+config-drift-per-path-added = `{ $rules }` newly suppressed under `{ $glob }`
+    .suggestion = Confirm that `{ $rules }` should not be reported under `{ $glob }`
+config-drift-per-path-removed = `{ $rules }` no longer suppressed under `{ $glob }`
+config-drift-baseline-grown = Baseline grew from { $from } to { $to } entries
+    .suggestion = Fix the new findings instead of baselining them, or confirm they are accepted debt
+config-drift-baseline-shrunk = Baseline shrank from { $from } to { $to } entries
+config-drift-fail-on-loosened = `fail_on` loosened from { $from } to { $to }
+    .suggestion = Confirm that runs with { $from } findings should pass
+config-drift-fail-on-tightened = `fail_on` tightened from { $from } to { $to }
+
+# Suppression comments (SUPP)
+suppressions-unknown-rule = Suppression names unknown rule `{ $token }`
+    .suggestion = Use a finding prefix (SEC), a module (error_handling) or a module:sub-rule (error_handling:swallowed)
+suppressions-did-you-mean = Did you mean `{ $candidate }`?
+suppressions-unknown-keyword = `{ $keyword }` is not a revet directive — this comment suppresses nothing
+    .suggestion = Write `revet-ignore` followed by the rules to silence, e.g. `revet-ignore SEC`
+suppressions-missing-space = `revet-ignore:{ $token }` needs a space after the colon — this comment suppresses nothing
+    .suggestion = Write `revet-ignore: { $token }`
+suppressions-no-rules = `revet-ignore` lists no rules — this comment suppresses nothing
+    .suggestion = List the rules to silence, e.g. `revet-ignore SEC`, or use `revet-ignore *` for all
+suppressions-separator = Rules after `{ $separator }` are ignored: only `{ $applied }` is suppressed, not `{ $ignored }`
+    .suggestion = Separate rules with spaces: `revet-ignore { $fixed }`
+suppressions-invalid-date = `until={ $value }` is not a date — this suppression never expires
+    .suggestion = Write the last day the suppression applies as YYYY-MM-DD, e.g. `until=2025-06-01`
+suppressions-unknown-option = `{ $option }=` is not a suppression option and is ignored
+    .suggestion = Use `until=YYYY-MM-DD` and `reason="..."`
+suppressions-expired = Suppression of `{ $rules }` expired on { $until }
+    .suggestion = Fix the findings it silenced, or move `until=` to a later date
+suppressions-expired-reason = Suppression of `{ $rules }` expired on { $until } ({ $reason })
+suppressions-useless = Suppression `revet-ignore { $rules }` matched no findings
+    .suggestion = Remove the suppression comment
+suppressions-useless-rule = `{ $token }` in this suppression matched no findings
+    .suggestion = Remove `{ $token }` from the suppression
+suppressions-misplaced = A finding it matches is on line { $line } — did you mean to place this on line { $line }?
+suppressions-misplaced-block = A finding it matches is on line { $line } — did you mean to place this on line { $line } or add `:block`?
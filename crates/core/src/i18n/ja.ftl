@@ -0,0 +1,356 @@
+# Japanese message catalog. Keys missing here fall back to English (en.ftl).
+
+# Shared
+platform-variants-note = { $finding } [プラットフォーム別の実装: { $platforms }]
+
+# Complexity (CMPLX)
+complexity-length-error = 関数 `{ $name }` は { $lines } 行あります（推奨上限: { $max } 行）
+    .suggestion = この関数を小さく目的の明確な関数に分割してください
+complexity-length-warning = 関数 `{ $name }` は { $lines } 行あります（推奨: { $max } 行未満）
+    .suggestion = この関数を小さく目的の明確な関数に分割することを検討してください
+complexity-parameters-error = 関数 `{ $name }` の引数が { $count } 個あります（推奨上限: { $max } 個）
+    .suggestion = 関連する引数を構造体や設定オブジェクトにまとめてください
+complexity-parameters-warning = 関数 `{ $name }` の引数が { $count } 個あります（推奨: { $max } 個未満）
+    .suggestion = 関連する引数を構造体やオブジェクトにまとめることを検討してください
+complexity-cyclomatic-error = 関数 `{ $name }` の循環的複雑度は { $score } です（推奨上限: { $max }）
+    .suggestion = ヘルパー関数の抽出やロジックの単純化で分岐を減らしてください
+complexity-cyclomatic-warning = 関数 `{ $name }` の循環的複雑度は { $score } です（推奨: { $max } 未満）
+    .suggestion = ヘルパー関数を抽出して分岐を減らすことを検討してください
+complexity-nesting-error = 関数 `{ $name }` のネストの深さは { $depth } です（推奨上限: { $max }）
+    .suggestion = 早期リターンやヘルパー関数でネストを浅くしてください
+complexity-nesting-warning = 関数 `{ $name }` のネストの深さは { $depth } です（推奨: { $max } 未満）
+    .suggestion = 早期リターンやヘルパー関数でネストを浅くすることを検討してください
+complexity-length-delta = 関数 `{ $name }` はこの変更で長くなりました: { $from } → { $to } 行（しきい値: { $max } 行）
+    .suggestion = この関数を伸ばさず、追加したコードを別の関数に移してください
+complexity-parameters-delta = 関数 `{ $name }` の引数がこの変更で増えました: { $from } → { $to } 個（しきい値: { $max } 個）
+    .suggestion = 新しい値は既存の構造体や設定オブジェクトにまとめて渡してください
+complexity-cyclomatic-delta = 関数 `{ $name }` の循環的複雑度がこの変更で上がりました: { $from } → { $to }（しきい値: { $max }）
+    .suggestion = 追加した分岐をヘルパー関数に切り出し、この関数の複雑度を上げないでください
+complexity-nesting-delta = 関数 `{ $name }` のネストがこの変更で深くなりました: { $from } → { $to }（しきい値: { $max }）
+    .suggestion = 新しいコードは早期リターンやヘルパー関数で書き、ネストを深くしないでください
+
+# Coupling (COUPLE)
+coupling-envy = 関数 `{ $name }` は `{ $file }` を { $foreign } 回、自身のファイルを { $home } 回参照しています
+    .suggestion = 関数を `{ $file }` 内の利用先の近くに移すか、その処理を `{ $file }` 側の 1 回の呼び出しにまとめてください
+coupling-intimacy = `{ $file }` と `{ $other }` は互いを多く参照しています: 一方向に { $forward } 本、逆方向に { $backward } 本、シンボル { $symbols } 個 (密度 { $density })
+    .suggestion = 2 つのファイルを統合するか、共有部分をどちらか一方または両方が依存するモジュールに移してください
+
+# Concurrency (RACE)
+concurrency-shared-state = `{ $var }` は `{ $function }` でロックなしに変更されており、`{ $entry }` から並行に実行されます
+    .suggestion = 変更をロック (`with lock:`) で保護するか、スレッドごとに状態を持たせて結果をまとめてください
+concurrency-handler-state = モジュールレベルの `{ $var }` が `{ $function }` で書き換えられており、`{ $entry }` から並行するリクエストごとに実行されます
+    .suggestion = リクエストごとの状態はリクエストに持たせ、共有する状態は並行アクセス向けのストアに置いてください
+concurrency-static-mut = `static mut { $name }` はどのスレッドからも同期なしに読み書きできます
+    .suggestion = アトミック型、`Mutex` または `OnceLock` を使ってください
+    .no-oncelock = アトミック型か、遅延初期化した static（`once_cell`、`lazy_static`）の `Mutex` を使ってください（`OnceLock` には Rust 1.70 が必要です）
+concurrency-unsafe-send-sync = `unsafe impl { $trait_name } for { $type_name }` はコンパイラが検証できないスレッド安全性を宣言しています
+    .suggestion = フィールドをスレッドセーフにしてコンパイラに導出させるか、スレッド間で共有しても安全な理由を文書化してください
+
+# Documentation (DOC)
+docs-undocumented = 公開シンボル `{ $name }` にドキュメントコメントがありません
+    .suggestion = 役割と使い方を説明するドキュメントコメントを追加してください
+docs-coverage = `{ $project }` のドキュメントカバレッジは { $percent }% (公開シンボル { $public } 個中 { $documented } 個) で、最小値 { $min }% を下回っています
+    .suggestion = `DOC` として報告された公開シンボルにドキュメントを書くか、`[analyzers.docs] min_coverage` を下げてください
+
+# Hotspots (HOT)
+hotspots-file = ホットスポット: { $days } 日間に { $changes } 回変更され、複雑度の合計は { $complexity } です
+    .suggestion = このファイルのリファクタリングを優先してください。分割するか、最も複雑な関数を単純化してください
+
+# Ownership (OWN)
+ownership-orphaned-months = 孤立したコード: 行の { $departed }% を最後に変更したのは離任したコントリビューター ({ $owners }) で、最終変更は { $months } か月前です
+ownership-orphaned-days = 孤立したコード: 行の { $departed }% を最後に変更したのは離任したコントリビューター ({ $owners }) で、最終変更は { $days } 日前です
+    .suggestion = CODEOWNERS で担当者を割り当てるか、変更が必要になる前にアクティブなメンテナーにこのコードをレビューしてもらってください
+
+# Slow files (PERF)
+performance-slow-file = 解析に時間がかかっています: { $analyzer } がこのファイルに { $ms } ms を要しました (しきい値 { $max } ms)
+    .suggestion = 生成・ベンダリングされたファイルなら除外し、そうでなければ分割するか速度低下を報告してください
+
+# Translations (I18N)
+i18n-missing-key = 翻訳キー `{ $key }` が { $locales } にありません
+    .suggestion = `{ $key }` を { $locales } のカタログに追加してください
+i18n-unused-key = 翻訳キー `{ $key }` はどこからも参照されていません（{ $locales }）
+    .suggestion = 実行時に組み立てるコードがなければ、`{ $key }` をカタログから削除してください
+i18n-missing-namespace = ロケール { $locale } に `{ $namespace }` 名前空間がありません（{ $default } には存在します）
+    .suggestion = { $locale } の `{ $namespace }` カタログを追加してください
+i18n-dynamic-key = 翻訳キー `{ $expr }` は実行時に組み立てられるため、カタログと照合できません
+    .suggestion = 欠けている翻訳を検出できるよう、値からキーへの対応表などを使ってリテラルのキーで参照してください
+
+# Feature flags (FLAG)
+flags-stale-launched = フラグ `{ $flag }` はローンチ済みです。チェックと不要になった分岐を削除してください
+    .suggestion = フラグが有効にするコードを残し、チェックとフォールバックを削除してください
+flags-stale-launched-since = フラグ `{ $flag }` は { $since } にローンチ済みです。チェックと不要になった分岐を削除してください
+    .suggestion = フラグが有効にするコードを残し、チェックとフォールバックを削除してください
+flags-stale-retired = フラグ `{ $flag }` は廃止済みです。チェックとそれが守るコードを削除してください
+    .suggestion = フォールバックを残し、チェックとフラグ有効時にだけ実行されていたコードを削除してください
+flags-stale-retired-since = フラグ `{ $flag }` は { $since } に廃止済みです。チェックとそれが守るコードを削除してください
+    .suggestion = フォールバックを残し、チェックとフラグ有効時にだけ実行されていたコードを削除してください
+flags-dead-branch-launched = { $start }–{ $end } 行目はローンチ済みのフラグ `{ $flag }` が無効なときにしか実行されません
+    .suggestion = { $line } 行目のチェックと一緒にこれらの行を削除してください
+flags-dead-branch-retired = { $start }–{ $end } 行目は廃止済みのフラグ `{ $flag }` が有効なときにしか実行されません
+    .suggestion = { $line } 行目のチェックと一緒にこれらの行を削除してください
+flags-unknown = フラグ `{ $flag }` はフラグ一覧（{ $inventory }）にありません
+    .suggestion = 状態とオーナーを付けて一覧に追加するか、名前の誤りを直してください
+flags-unused = フラグ `{ $flag }` は一覧にありますが、どのコードもチェックしていません
+    .suggestion = フラグ管理システムでアーカイブしたら retired にするか、エントリを削除してください
+flags-dynamic = フラグ名 `{ $expr }` は実行時に組み立てられるため、一覧と照合できません
+    .suggestion = 古いフラグや未知のフラグを検出できるよう、フラグ名は文字列リテラルで渡してください
+flags-invalid-inventory = フラグ一覧を読み込めません: { $error }
+    .suggestion = エントリを修正してください。各 [[flag]] には一意の name と active・launched・retired のいずれかの state が必要です
+
+# Refactor verification (REF)
+refactor-arity-mismatch = `{ $name }` の呼び出しは引数を { $count } 個渡していますが、この変更でシグネチャが `{ $new }` になりました（変更前: `{ $old }`）
+    .suggestion = { $file } の変更で更新されなかったこの呼び出しを直すか、新しい引数にデフォルト値を付けてください
+refactor-stale-reference = `{ $old }` はもう存在しません。この変更で { $file } の `{ $new }` に名前が変わりました
+    .suggestion = ここでは `{ $new }` を使うか、すべての呼び出し元を更新するまで `{ $old }` を別名として残してください
+
+# Debug artifacts (DBG)
+debug-debugger = デバッガ文 `{ $call }` がコードに残っています
+    .suggestion = 削除してください。この行が実行されるたびにプログラムが停止します
+debug-print = `{ $call }` はデバッグ用の出力が残ったもののようです
+    .suggestion = 削除するか、何が起きたかを示すメッセージ付きでプロジェクトのロガーから出力してください
+debug-commented-code = { $lines } 行のコードがコメントアウトされています（{ $start }–{ $end } 行目）
+    .suggestion = ブロックを削除してください。以前のコードはバージョン管理に残っています
+
+# Test quality (TEST)
+test-sleep = テストコードに固定待機 `{ $call }` があります
+    .suggestion = スリープではなく条件そのものを待ってください（タイムアウト付きのポーリング、フェイククロック、イベントなど）
+test-no-assertions = テスト `{ $test }` は何も検証していません
+    .suggestion = 結果を検証するか、検証を行うヘルパーを [analyzers.test_quality] assertion_patterns に追加してください
+test-stale-skip = テストが `{ $marker }` により { $days } 日間無効化されています
+    .suggestion = テストを修正して有効に戻すか、削除してください
+test-broad-exception = `{ $assertion }` はどの例外でも成功します
+    .suggestion = コードが送出する具体的な例外型と、必要に応じてメッセージを検証してください
+
+# Stale copy (COPY)
+copy-copyright-year = 著作権表示の年が { $year } のままですが、現在は { $current } 年です
+    .suggestion = 年を { $current } に更新するか、ページの生成時に現在の日付から埋め込んでください
+copy-version-reference = 文言がバージョン { $version } に言及していますが、{ $current } より { $behind } メジャーバージョン古いものです
+    .suggestion = 言及を更新するか削除してください。「新機能」の案内はリリースのたびに古くなります
+copy-banned-term = `{ $term }` は使われなくなった名称です
+    .suggestion = 代わりに `{ $replacement }` を使ってください
+    .reword = `{ $term }` を使わない表現に書き換えてください
+
+# Numeric precision (NUM)
+numeric-money-float = `{ $name }` は金額を2進浮動小数点数で保持しています
+    .python = `decimal.Decimal`（float ではなく文字列から生成）か、セント単位の `int` を使ってください
+    .javascript = セント単位の整数か、decimal.js や big.js などの10進数ライブラリを使ってください
+    .java = `BigDecimal`（double ではなく文字列から生成）か、セント単位の `long` を使ってください
+    .go = セント単位の `int64` か、github.com/shopspring/decimal などの10進数パッケージを使ってください
+    .rust = セント単位の `i64` か、`rust_decimal::Decimal` などの10進数型を使ってください
+    .csharp = `decimal` を使ってください
+numeric-float-equality = `{ $expression }` は浮動小数点数を厳密に比較しています
+    .suggestion = 許容誤差の範囲で比較する（`math.isclose`、`Math.abs(a - b) < epsilon`）か、値を整数や10進数で保持してください
+numeric-unsafe-id-literal = ID `{ $name }` に { $literal } が設定されていますが、JavaScript の数値が保持できる桁数を超えています
+    .suggestion = ID を文字列か BigInt リテラル（`{ $literal }n`）で記述してください
+    .no-bigint = ID を文字列で記述してください（BigInt リテラルには ES2020 以降のターゲットが必要です）
+numeric-unsafe-id-parsed = ID `{ $name }` は `JSON.parse` から得ていますが、2^53 を超える整数は丸められます
+    .suggestion = API から ID を文字列で送るか、reviver や json-bigint などのライブラリで解析してください
+
+# Pagination (PAGE)
+pagination-unbounded = `{ $handler }` は `{ $query }` の結果をページングせずにすべて返しています
+    .prisma = `findMany` に `take` と `skip` または `cursor` を渡し、`limit`/`cursor` クエリパラメータから値を取ってください
+    .fastify = `request.query` から `limit` と `cursor` を読んでクエリを制限するか、Fastify のページネーションプラグインを使ってください
+    .django = DRF のページネーションクラス（`PageNumberPagination`、`LimitOffsetPagination`、`CursorPagination`）を設定するか、クエリセットをスライスしてください
+    .sqlalchemy = `limit` と `offset`（またはカーソル）のパラメータを受け取り、クエリに `.limit()`/`.offset()` を適用してください
+    .sequelize = `findAll` に `limit` と `offset` を渡すか、それらを指定して `findAndCountAll` を使ってください
+    .mongo = `find` に `.limit()` と `.skip()` をつなげるか、カーソルで絞り込んでください
+    .sql = `LIMIT` と `OFFSET`、またはインデックス列でのキーセットページネーションをリクエストパラメータから適用してください
+
+# Accessibility (A11Y)
+a11y-img-alt = `<{ $tag }>` に代替テキストがありません
+    .suggestion = 画像を説明する `alt="…"` を追加するか、装飾のみの画像なら `alt=""` を指定してください
+a11y-click-events = `<{ $tag }>` はクリックを処理しますが、キーボードハンドラも role もありません
+    .suggestion = `<button>` を使うか、キーボードやスクリーンリーダーの利用者が操作できるよう `{ $attributes }` を追加してください
+a11y-label = フォーム項目 `<{ $tag }>` に関連付けられたラベルがありません
+    .suggestion = `<label>` で囲むか、`{ $label }` で参照するか、`aria-label="…"` を追加してください
+a11y-html-lang = `<html>` に lang 属性がありません
+    .suggestion = ページの言語を示す `lang="…"`（例: `lang="en"`）を追加してください
+a11y-tabindex = `<{ $tag }>` の `{ $attribute }` により、本来のタブ順より先にフォーカスされます
+    .suggestion = `{ $fix }` を使い、フォーカスの移動順どおりにマークアップを並べてください
+
+# Web security configuration (WEBSEC)
+websec-cors-wildcard-credentials = { $pattern }: CORS が資格情報付きですべてのオリジン（{ $origin }）を許可しています
+    .suggestion = 信頼するオリジンを列挙してください（`{ $setting }`）。資格情報付きでは、どのサイトからも認証済みリクエストを送りレスポンスを読めてしまいます
+    .runtime = { $pattern }: CORS は資格情報を許可しており、すべてのオリジンを許可するかは `{ $value }` しだいです。信頼するオリジンだけに資格情報が渡ることを実行時に確認してください
+websec-cookie-httponly = { $pattern }: Cookie `{ $cookie }` に HttpOnly が付いていません
+    .suggestion = `{ $setting }` を指定し、注入されたものを含めスクリプトから Cookie を読めないようにしてください
+    .runtime = { $pattern }: Cookie `{ $cookie }` の HttpOnly は `{ $value }` から決まります。実行時に有効であることを確認してください
+websec-cookie-secure = { $pattern }: Cookie `{ $cookie }` に Secure が付いていません
+    .suggestion = `{ $setting }` を指定し、平文の HTTP で Cookie が送られないようにしてください
+    .runtime = { $pattern }: Cookie `{ $cookie }` の Secure は `{ $value }` から決まります。実行時に有効であることを確認してください
+websec-cookie-samesite = { $pattern }: Cookie `{ $cookie }` に SameSite が付いていません
+    .suggestion = `{ $setting }` を指定し、クロスサイトのリクエストに Cookie が付かないようにしてください
+    .runtime = { $pattern }: Cookie `{ $cookie }` の SameSite は `{ $value }` から決まります。実行時に設定されていることを確認してください
+websec-headers-disabled = { $pattern }: `{ $header }` が無効になっています
+    .suggestion = `{ $setting }` を使い、支障のあるヘッダーだけを理由をコメントに書いたうえで無効にしてください
+    .remove = `{ $header }` を無効にしている呼び出しを削除し、支障のあるヘッダーだけを理由をコメントに書いたうえで無効にしてください
+    .runtime = { $pattern }: `{ $header }` は `{ $value }` から設定されます。実行時に有効のままであることを確認してください
+websec-headers-missing = { $pattern }: このファイルではセキュリティヘッダーのミドルウェア（{ $middleware }）が適用されていません
+    .suggestion = `{ $setting }` を追加するか、プロキシや別のモジュールがヘッダーを設定している場合はこの指摘を抑制してください
+
+# API contract (CONTRACT)
+contract-unmatched-call = `{ $route }`（{ $callee }）に対応するバックエンドのエンドポイントがありません
+    .method = `{ $route }`（{ $callee }）に対応するバックエンドのエンドポイントがありません。このパスは { $methods } で提供されています
+    .closest = `{ $endpoint }`（{ $location }）の誤りではありませんか？パスかメソッドを修正するか、エンドポイントを追加してください
+    .suggestion = パスかメソッドを修正するか、エンドポイントを追加してください。別サービスへの呼び出しであればこの指摘を抑制してください
+contract-uncalled-endpoint = `{ $route }`（{ $handler }）はこのリポジトリ内のどこからも呼び出されていません
+    .suggestion = リポジトリ外からも使われていなければエンドポイントを削除し、公開 API であれば `contract:uncalled_endpoint` を無効にしてください
+contract-field-mismatch = `{ $route }` に { $model } が受け付けないフィールド（{ $extra }）が送られ、必須フィールド（{ $missing }）が欠けています
+    .extra = `{ $route }` に { $model } が受け付けないフィールドが送られています: { $extra }
+    .missing = `{ $route }` に送られるボディに { $model } の必須フィールドが欠けています: { $missing }
+    .suggestion = リクエストボディを { $model }（{ $location }）に合わせてください
+contract-dynamic-url = `{ $callee }` は URL を実行時に組み立てています（`{ $expr }`）。エンドポイントと照合できません
+    .suggestion = 照合できるよう、リテラルのパスかテンプレートリテラルを使ってください
+
+# Repository (REPO)
+repository-license = リポジトリのルートに LICENSE ファイルがありません
+    .suggestion = コードの利用条件を記した LICENSE（または COPYING）ファイルを追加してください
+repository-stale-baseline = ベースラインには { $entries } 件の指摘があり、{ $days } 日前に作成されたままです
+    .suggestion = ベースラインの指摘を修正するか期限を設定し、`revet baseline` でベースラインを作り直してください
+repository-no-security = `[modules] security` が無効のため、セキュリティアナライザーが一つも実行されません
+    .suggestion = `security` を有効に戻し、個々の指摘を抑制してください
+repository-codeowners = `{ $branch }` は保護ブランチとして宣言されていますが、CODEOWNERS ファイルがありません
+    .suggestion = `{ $branch }` への変更にレビュアーが付くよう、ルート、`.github/` または `docs/` に CODEOWNERS を追加してください
+repository-required-check = 必須チェック `{ $check }` は `.github/workflows` のどのワークフローのジョブでもありません
+    .suggestion = その ID または `name` のジョブを追加するか、`[analyzers.repository]` の `required_checks` を更新してください
+
+# Upgrade advisor (UPGRADE)
+upgrade-removed = `{ $api }` は { $runtime } { $version } で削除されました（目標 { $target }）
+upgrade-changed = `{ $api }` は { $runtime } { $version } から動作が変わりました（目標 { $target }）
+upgrade-changed-note = `{ $api }` は { $runtime } { $version } で変更されました（目標 { $target }）: { $note }
+upgrade-deprecated = `{ $api }` は { $runtime } { $version } から非推奨です（目標 { $target }）
+upgrade-deprecated-removal = `{ $api }` は { $runtime } { $version } から非推奨で、{ $removal } で削除されます（目標 { $target }）
+upgrade-use-instead = 代わりに { $replacement } を使ってください
+upgrade-remove-use = アップグレードの前に `{ $api }` の使用をやめてください
+upgrade-invalid-pack = アップグレードパックを読み込めません: { $error }
+    .suggestion = パックを修正するか `[upgrade] packs` から外してください。各 [[api]] には module と deprecated・changed・removed のいずれかのバージョンが必要です
+
+# Import boundaries (BOUNDARY)
+boundary-test-import = プロダクションコードがテストコードをインポートしています: `{ $module }`
+    .suggestion = 共通のコードをテスト用のディレクトリの外に移すか、テストからのみインポートしてください
+boundary-dev-dependency = プロダクションコードが開発用の依存パッケージ `{ $module }` をインポートしています
+    .suggestion = パッケージを実行時の依存関係に移すか、プロダクションコードからインポートしないでください
+
+# Circular imports (CYCLE)
+circular-imports-cycle = 循環インポートを検出しました: { $cycle }
+    .suggestion = 共通のコードを別モジュールに切り出して循環を解消してください
+
+# Dead imports (DIMPORT)
+dead-imports-unused = `{ $name }` はインポートされていますが使用されていません
+    .suggestion = `{ $name }` の未使用インポートを削除してください
+
+# Error handling (ERR)
+error-handling-empty-catch = 空の catch/except ブロック
+    .suggestion = エラーを処理するか、無視しても安全な理由をコメントで説明してください
+error-handling-bare-except = 例外型を指定していない except
+    .suggestion = 例外型を指定してください: except ValueError: または except Exception as e:
+error-handling-unwrap = .unwrap() の呼び出し
+    .suggestion = ? 演算子、.unwrap_or()、.unwrap_or_else()、または説明的なメッセージ付きの .expect() を使用してください
+error-handling-panic = テスト以外のコードでの panic!/todo!/unimplemented!
+    .suggestion = panic する代わりに、説明的なエラーを持つ Result を返してください
+error-handling-expect-message = 説明的でないメッセージの .expect()
+    .suggestion = 説明的なメッセージを使用してください: .expect("Failed to open config file")
+error-handling-swallowed = catch ブロックがエラーをログ出力するだけです
+    .suggestion = ログ出力後にエラーを再スローするか、適切に処理してください
+error-handling-broad-except = 範囲が広すぎる例外の捕捉
+    .suggestion = より具体的な例外型を捕捉してください（例: ValueError、KeyError）
+error-handling-empty-promise-catch = 空の .catch() コールバック
+    .suggestion = .catch() コールバック内でエラーを処理するか再スローしてください
+error-handling-discarded-error = Go でエラーが破棄されています
+    .suggestion = エラーを処理してください: if err != nil { "{" } return err { "}" }
+
+# Secret exposure (SEC, HIST)
+secrets-detected = { $kind } の可能性があります（{ $value }）
+secrets-in-history = git 履歴に { $kind } の可能性があります（{ $value }、コミット { $commit }、HEAD には存在しません）
+    .suggestion = この認証情報をローテーションしてください — HEAD から削除しても git 履歴からは消えません
+secrets-dotenv = { $key } に { $kind } の可能性があります（{ $value }）
+secrets-dotenv-value = シークレット値
+    .suggestion = 実際の認証情報はリポジトリに含めず、プレースホルダーを記載した .env.example をコミットし、値は環境変数またはシークレットマネージャーから読み込んでください
+secrets-tracked-env = { $file } が git で管理されています
+    .suggestion = `git rm --cached { $file }` を実行して .gitignore に追加し、含まれるすべての認証情報をローテーションしてください。代わりにプレースホルダーを記載した .env.example をコミットしてください
+secrets-aws-access-key-id = AWS アクセスキー ID
+    .suggestion = 代わりに環境変数 AWS_ACCESS_KEY_ID を使用してください
+secrets-aws-secret-access-key = AWS シークレットアクセスキー
+    .suggestion = 代わりに環境変数 AWS_SECRET_ACCESS_KEY を使用してください
+secrets-github-token = GitHub トークン
+    .suggestion = 代わりに環境変数 GITHUB_TOKEN を使用してください
+secrets-private-key-pem = 秘密鍵 (PEM)
+    .suggestion = 秘密鍵はリポジトリ外のファイルに保存し、パスで参照してください
+secrets-database-connection-string = データベース接続文字列
+    .suggestion = 接続文字列は .env ファイルに保存するか、シークレットマネージャーを使用してください
+secrets-stripe-secret-restricted-key-live = Stripe シークレット / 制限付きキー（本番）
+    .suggestion = Stripe のキーは環境変数に保存し、本番キーは決してコミットしないでください
+secrets-slack-token = Slack トークン
+    .suggestion = Slack トークンは環境変数またはシークレットマネージャーに保存してください
+secrets-sendgrid-api-key = SendGrid API キー
+    .suggestion = SendGrid API キーは環境変数 SENDGRID_API_KEY に保存してください
+secrets-twilio-auth-token = Twilio 認証トークン
+    .suggestion = Twilio 認証トークンは環境変数 TWILIO_AUTH_TOKEN に保存してください
+secrets-azure-storage-connection-string = Azure Storage 接続文字列
+    .suggestion = Azure の接続文字列は環境変数または Azure Key Vault に保存してください
+secrets-stripe-publishable-key-live = Stripe 公開可能キー（本番）
+    .suggestion = 公開可能キーであってもハードコードせず、設定に保存してください
+secrets-gcp-service-account-email = GCP サービスアカウントのメールアドレス
+    .suggestion = GCP サービスアカウントキーの JSON をソースコードに埋め込まず、Workload Identity またはシークレットマネージャーを使用してください
+secrets-base64-secret = 機密性の高い変数内の Base64 エンコードされたシークレット
+    .suggestion = Base64 エンコードされたシークレットの可能性があります。認証情報はエンコードしてソースに置かず、環境変数またはシークレットマネージャーに保存してください
+secrets-generic-api-key = 汎用 API キー
+    .suggestion = API キーは環境変数または .env ファイルに保存してください
+secrets-generic-secret-key = 汎用シークレットキー
+    .suggestion = シークレットキーは環境変数または .env ファイルに保存してください
+secrets-hardcoded-password = ハードコードされたパスワード
+    .suggestion = パスワードは環境変数に保存するか、シークレットマネージャーを使用してください
+secrets-custom = { $name }
+    .suggestion = この認証情報は環境変数またはシークレットマネージャーに保存してください
+
+# Configuration drift (CONF)
+config-drift-module-disabled = この変更により `{ $module }` アナライザーが無効になります
+    .suggestion = `{ $module }` の検出を報告しなくてよいか確認するか、モジュールを有効のままにしてください
+config-drift-module-enabled = この変更により `{ $module }` アナライザーが有効になります
+config-drift-severity-lowered = `[severity] { $id }` が { $from } から { $to } に下げられました
+    .suggestion = `{ $id }` の検出を { $to } として報告してよいか確認してください
+config-drift-severity-raised = `[severity] { $id }` が { $from } から { $to } に上げられました
+config-drift-threshold-raised = `{ $key }` が { $from } から { $to } に引き上げられました — 報告される検出が減ります
+    .suggestion = 新しい上限を確認するか、以前の上限を超える検出を修正してください
+config-drift-threshold-lowered = `{ $key }` が { $from } から { $to } に引き下げられました
+config-drift-path-excluded = `{ $value }` が新たに解析対象から除外されました
+    .suggestion = `{ $value }` に一致するファイルをレビュー対象外にしてよいか確認してください
+config-drift-path-included = `{ $value }` は解析対象から除外されなくなりました
+config-drift-finding-ignored = `{ $value }` の検出が新たにすべての場所で無視されます
+    .suggestion = `{ $value }` をすべての場所で無視する代わりに、個々の検出を `revet-ignore` で抑制してください
+config-drift-finding-unignored = `{ $value }` の検出は無視されなくなりました
+config-drift-per-path-added = `{ $glob }` 以下で `{ $rules }` が新たに抑制されます
+    .suggestion = `{ $glob }` 以下で `{ $rules }` を報告しなくてよいか確認してください
+config-drift-per-path-removed = `{ $glob }` 以下で `{ $rules }` は抑制されなくなりました
+config-drift-baseline-grown = ベースラインのエントリが { $from } 件から { $to } 件に増えました
+    .suggestion = 新しい検出をベースラインに追加せず修正するか、許容する負債であることを確認してください
+config-drift-baseline-shrunk = ベースラインのエントリが { $from } 件から { $to } 件に減りました
+config-drift-fail-on-loosened = `fail_on` が { $from } から { $to } に緩められました
+    .suggestion = { $from } の検出があっても実行を成功させてよいか確認してください
+config-drift-fail-on-tightened = `fail_on` が { $from } から { $to } に厳しくされました
+
+# Suppression comments (SUPP)
+suppressions-unknown-rule = 抑制コメントが存在しないルール `{ $token }` を指定しています
+    .suggestion = 検出 ID の接頭辞（SEC）、モジュール名（error_handling）、またはモジュール:サブルール（error_handling:swallowed）を指定してください
+suppressions-did-you-mean = `{ $candidate }` の誤りではありませんか？
+suppressions-unknown-keyword = `{ $keyword }` は revet のディレクティブではありません — このコメントは何も抑制しません
+    .suggestion = `revet-ignore` の後に抑制するルールを書いてください（例: `revet-ignore SEC`）
+suppressions-missing-space = `revet-ignore:{ $token }` はコロンの後に空白が必要です — このコメントは何も抑制しません
+    .suggestion = `revet-ignore: { $token }` と書いてください
+suppressions-no-rules = `revet-ignore` にルールが指定されていません — このコメントは何も抑制しません
+    .suggestion = 抑制するルールを指定してください（例: `revet-ignore SEC`）。すべて抑制する場合は `revet-ignore *` を使用してください
+suppressions-separator = `{ $separator }` 以降のルールは無視されます: 抑制されるのは `{ $applied }` のみで、`{ $ignored }` は抑制されません
+    .suggestion = ルールは空白で区切ってください: `revet-ignore { $fixed }`
+suppressions-invalid-date = `until={ $value }` は日付ではありません — この抑制は期限切れになりません
+    .suggestion = 抑制を適用する最終日を YYYY-MM-DD 形式で記述してください（例: `until=2025-06-01`）
+suppressions-unknown-option = `{ $option }=` は抑制のオプションではないため無視されます
+    .suggestion = `until=YYYY-MM-DD` と `reason="..."` を使用してください
+suppressions-expired = `{ $rules }` の抑制は { $until } に期限切れになりました
+    .suggestion = 抑制していた検出を修正するか、`until=` をより後の日付に変更してください
+suppressions-expired-reason = `{ $rules }` の抑制は { $until } に期限切れになりました（{ $reason }）
+suppressions-useless = 抑制コメント `revet-ignore { $rules }` に該当する検出はありません
+    .suggestion = この抑制コメントを削除してください
+suppressions-useless-rule = この抑制コメントの `{ $token }` に該当する検出はありません
+    .suggestion = 抑制コメントから `{ $token }` を削除してください
+suppressions-misplaced = 該当する検出は { $line } 行目にあります — この抑制コメントを { $line } 行目に置くつもりでしたか？
+suppressions-misplaced-block = 該当する検出は { $line } 行目にあります — この抑制コメントを { $line } 行目に置くか、`:block` を付けるつもりでしたか？
@@ -0,0 +1,83 @@
+//! gitleaks JSON reports (`gitleaks detect --report-format json`)
+//!
+//! Every leak is an error under the rule `EXT-gitleaks-<RuleID>`, reported
+//! with the rule's `Description`. The secret itself is never copied into
+//! the message; when the file still holds it on the reported line, its
+//! span is recorded so outputs mask it in source excerpts.
+
+use super::{finding, ImportOptions};
+use crate::finding::{Finding, Severity};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// One entry of a gitleaks report
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Leak {
+    #[serde(rename = "RuleID")]
+    rule_id: String,
+    #[serde(default)]
+    description: String,
+    file: String,
+    #[serde(default)]
+    start_line: usize,
+    #[serde(default)]
+    end_line: usize,
+    #[serde(default)]
+    start_column: usize,
+    #[serde(default)]
+    end_column: usize,
+    #[serde(default)]
+    secret: String,
+    #[serde(default)]
+    commit: String,
+}
+
+/// Findings in a gitleaks report
+pub fn parse(content: &str, options: &ImportOptions) -> Result<Vec<Finding>> {
+    let leaks: Option<Vec<Leak>> =
+        serde_json::from_str(content).context("Not a gitleaks JSON report")?;
+    let findings = leaks
+        .unwrap_or_default()
+        .into_iter()
+        .map(|leak| {
+            let mut message = if leak.description.is_empty() {
+                format!("Secret detected by gitleaks rule {}", leak.rule_id)
+            } else {
+                leak.description.clone()
+            };
+            if !leak.commit.is_empty() {
+                let short = leak.commit.get(..8).unwrap_or(&leak.commit);
+                message.push_str(&format!(" (commit {})", short));
+            }
+            let mut finding = finding(
+                "gitleaks",
+                &leak.rule_id,
+                Severity::Error,
+                message,
+                options.resolve(&leak.file),
+                leak.start_line,
+            );
+            if leak.start_column > 0 {
+                finding.column = Some(leak.start_column);
+                if leak.end_line == leak.start_line && leak.end_column >= leak.start_column {
+                    finding.end_column = Some(leak.end_column + 1);
+                }
+            }
+            finding.secret_spans = secret_span(&finding, &leak.secret).into_iter().collect();
+            finding
+        })
+        .collect();
+    Ok(findings)
+}
+
+/// Byte span of `secret` on the finding's line, if the file still has it
+fn secret_span(finding: &Finding, secret: &str) -> Option<(usize, usize)> {
+    if secret.is_empty() {
+        return None;
+    }
+    let content = std::fs::read_to_string(&finding.file).ok()?;
+    let line = content.lines().nth(finding.line.checked_sub(1)?)?;
+    let start = line.find(secret)?;
+    Some((start, start + secret.len()))
+}
@@ -0,0 +1,227 @@
+//! Findings imported from external tools (`revet import`)
+//!
+//! Reports of other scanners are converted into [`Finding`]s so one run
+//! reports, baselines and gates everything. Each imported finding keeps its
+//! location and severity, gets an `EXT-NNN` ID and the namespaced rule
+//! `EXT-<tool>-<ruleId>` (what baselines and `revet-ignore` key on), and
+//! records the reporting tool in [`Finding::source_tool`].
+//!
+//! Reviews merge imported findings right after the analyzers (see
+//! [`merge`]), so inline and per-path suppression, the baseline and
+//! `[[policy]]` treat them like any other. Merging drops an imported
+//! finding that repeats one already on the same line of the same file:
+//! any finding with the same rule, or a revet finding of the category the
+//! external rule belongs to — a gitleaks leak next to a `SEC` finding, say.
+//!
+//! Paths in the reports are relative to wherever the tool ran. They are
+//! resolved against the repository root after removing
+//! [`ImportOptions::strip_prefix`]; absolute paths are kept when they lie
+//! under the root.
+
+pub mod gitleaks;
+pub mod sarif;
+pub mod semgrep;
+
+use crate::analyzer::registry::prefix_of;
+use crate::finding::{Finding, Severity};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// ID prefix of imported findings
+pub const PREFIX: &str = "EXT";
+
+/// Report formats `revet import --from` reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// SARIF 2.1.0 from any tool; the tool is named by `tool.driver.name`
+    Sarif,
+    /// `gitleaks detect --report-format json`
+    Gitleaks,
+    /// `semgrep --json`
+    Semgrep,
+}
+
+impl ImportFormat {
+    pub const ALL: &'static [&'static str] = &["sarif", "gitleaks", "semgrep"];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "sarif" => Some(Self::Sarif),
+            "gitleaks" => Some(Self::Gitleaks),
+            "semgrep" => Some(Self::Semgrep),
+            _ => None,
+        }
+    }
+}
+
+/// Where imported paths are resolved
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    /// Root the findings' paths are made absolute under
+    pub repo_root: PathBuf,
+    /// Leading path the tool saw that the repository root stands for
+    /// (`/github/workspace`, `./service/`); removed before resolving
+    pub strip_prefix: Option<String>,
+}
+
+impl ImportOptions {
+    pub fn new(repo_root: &Path) -> Self {
+        Self {
+            repo_root: repo_root.to_path_buf(),
+            strip_prefix: None,
+        }
+    }
+
+    /// Absolute path of `reported` (a path or `file://` URI from a report)
+    pub fn resolve(&self, reported: &str) -> PathBuf {
+        let mut path = reported
+            .strip_prefix("file://")
+            .map(percent_decode)
+            .unwrap_or_else(|| reported.to_string())
+            .replace('\\', "/");
+        if let Some(prefix) = &self.strip_prefix {
+            let prefix = prefix.trim_end_matches('/');
+            if let Some(rest) = path.strip_prefix(prefix) {
+                if prefix.is_empty() || rest.is_empty() || rest.starts_with('/') {
+                    path = rest.trim_start_matches('/').to_string();
+                }
+            }
+        }
+        let path = Path::new(path.trim_start_matches("./"));
+        if !path.is_absolute() {
+            return self.repo_root.join(path);
+        }
+        match path.strip_prefix(&self.repo_root) {
+            Ok(rel) => self.repo_root.join(rel),
+            Err(_) => path.to_path_buf(),
+        }
+    }
+}
+
+/// Read `path` as a `format` report
+pub fn load(format: ImportFormat, path: &Path, options: &ImportOptions) -> Result<Vec<Finding>> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("Cannot read {}", path.display()))?;
+    let findings = match format {
+        ImportFormat::Sarif => sarif::parse(&content, options),
+        ImportFormat::Gitleaks => gitleaks::parse(&content, options),
+        ImportFormat::Semgrep => semgrep::parse(&content, options),
+    };
+    findings.with_context(|| format!("Cannot import {}", path.display()))
+}
+
+/// Namespaced rule of a finding `tool` reported under `rule_id`
+pub fn rule(tool: &str, rule_id: &str) -> String {
+    format!("{}-{}-{}", PREFIX, tool, rule_id)
+}
+
+/// Tool name as it appears in rules: lowercase, up to the first space
+/// (`Semgrep OSS` → `semgrep`)
+pub fn tool_name(name: &str) -> String {
+    let name = name.split_whitespace().next().unwrap_or("external");
+    name.to_lowercase()
+}
+
+/// An imported finding at `file:line`
+pub(crate) fn finding(
+    tool: &str,
+    rule_id: &str,
+    severity: Severity,
+    message: String,
+    file: PathBuf,
+    line: usize,
+) -> Finding {
+    Finding {
+        id: format!("{}-000", PREFIX),
+        severity,
+        message,
+        file,
+        line: line.max(1),
+        rule: Some(rule(tool, rule_id)),
+        source_tool: Some(tool.to_string()),
+        ..Default::default()
+    }
+}
+
+/// Revet categories (ID prefixes) external rules fall into, by keyword in
+/// the rule id
+const CATEGORIES: &[(&str, &[&str])] = &[
+    (
+        "SEC",
+        &[
+            "secret",
+            "credential",
+            "password",
+            "api-key",
+            "apikey",
+            "token",
+            "private-key",
+        ],
+    ),
+    ("SQL", &["sql"]),
+    (
+        "CMD",
+        &["command-injection", "os-command", "subprocess", "shell"],
+    ),
+    ("DESER", &["deserial", "pickle", "unsafe-yaml"]),
+    ("SSRF", &["ssrf"]),
+    ("PATH", &["path-traversal"]),
+];
+
+/// Revet category of an imported finding: gitleaks only reports secrets,
+/// other tools are matched by rule id
+fn category(finding: &Finding) -> Option<&'static str> {
+    if finding.source_tool.as_deref() == Some("gitleaks") {
+        return Some("SEC");
+    }
+    let rule = finding.rule.as_deref()?.to_lowercase();
+    CATEGORIES
+        .iter()
+        .find(|(_, keywords)| keywords.iter().any(|k| rule.contains(k)))
+        .map(|(prefix, _)| *prefix)
+}
+
+/// Add `imported` to a run's `findings`, numbered `EXT-001` onwards.
+/// Returns how many were dropped as duplicates of findings on the same
+/// line (see the module docs).
+pub fn merge(findings: &mut Vec<Finding>, imported: Vec<Finding>) -> usize {
+    let mut dropped = 0;
+    let mut number = 0;
+    for mut finding in imported {
+        let category = category(&finding);
+        let duplicate = findings.iter().any(|other| {
+            other.file == finding.file
+                && other.line == finding.line
+                && (other.rule.is_some() && other.rule == finding.rule
+                    || other.source_tool.is_none() && category == Some(prefix_of(&other.id)))
+        });
+        if duplicate {
+            dropped += 1;
+            continue;
+        }
+        number += 1;
+        finding.id = format!("{}-{:03}", PREFIX, number);
+        findings.push(finding);
+    }
+    dropped
+}
+
+/// `%XX` escapes in a `file://` URI
+fn percent_decode(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+            if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
@@ -0,0 +1,174 @@
+//! SARIF 2.1.0 reports from any tool
+//!
+//! Every run is read with its tool named by `tool.driver.name`. A result's
+//! rule comes from `ruleId` (or `rule.id`, or the driver rule at
+//! `ruleIndex`) and its severity from `level`, falling back to the rule's
+//! `defaultConfiguration.level` and then to SARIF's default, `warning`. The
+//! first location is kept; `uriBaseId`s are expanded with the run's
+//! `originalUriBaseIds`. A result without a physical location becomes a
+//! repository-level finding. Results whose `kind` is `pass` or `notApplicable`,
+//! and results suppressed in the report, are skipped.
+
+use super::{finding, tool_name, ImportOptions};
+use crate::finding::{Finding, Severity};
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// Findings in a SARIF document
+pub fn parse(content: &str, options: &ImportOptions) -> Result<Vec<Finding>> {
+    let log: Value = serde_json::from_str(content).context("Invalid SARIF JSON")?;
+    let Some(runs) = log.get("runs").and_then(Value::as_array) else {
+        bail!("Not a SARIF log: no \"runs\" array");
+    };
+    let mut findings = Vec::new();
+    for run in runs {
+        let tool = tool_name(str_at(run, &["tool", "driver", "name"]).unwrap_or("sarif"));
+        let rules = run
+            .pointer("/tool/driver/rules")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let results = run
+            .get("results")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for result in results {
+            if matches!(
+                result.get("kind").and_then(Value::as_str),
+                Some("pass" | "notApplicable")
+            ) || is_suppressed(result)
+            {
+                continue;
+            }
+            let rule = rule_of(result, rules);
+            let rule_id = result
+                .get("ruleId")
+                .and_then(Value::as_str)
+                .or_else(|| str_at(result, &["rule", "id"]))
+                .or_else(|| rule.and_then(|r| r.get("id")).and_then(Value::as_str))
+                .unwrap_or("unknown");
+            let level = result
+                .get("level")
+                .and_then(Value::as_str)
+                .or_else(|| rule.and_then(|r| str_at(r, &["defaultConfiguration", "level"])))
+                .unwrap_or("warning");
+            let location = result.pointer("/locations/0/physicalLocation");
+            let region = location.and_then(|l| l.get("region"));
+            let number = |key: &str| {
+                region
+                    .and_then(|r| r.get(key))
+                    .and_then(Value::as_u64)
+                    .map(|n| n as usize)
+            };
+            let start_line = number("startLine").unwrap_or(1);
+
+            let mut finding = finding(
+                &tool,
+                rule_id,
+                severity(level),
+                message(result, rule).unwrap_or_else(|| rule_id.to_string()),
+                PathBuf::new(),
+                start_line,
+            );
+            match location {
+                Some(location) => {
+                    let Some(uri) = artifact_uri(location, run) else {
+                        continue;
+                    };
+                    finding.file = options.resolve(&uri);
+                    finding.column = number("startColumn");
+                    if number("endLine").is_none_or(|end| end == start_line) {
+                        finding.end_column = number("endColumn");
+                    }
+                }
+                None => finding.line = 0,
+            }
+            finding.rule_description = rule
+                .and_then(|r| str_at(r, &["shortDescription", "text"]))
+                .map(str::to_string);
+            findings.push(finding);
+        }
+    }
+    Ok(findings)
+}
+
+/// SARIF `level` as a severity
+pub fn severity(level: &str) -> Severity {
+    match level {
+        "error" => Severity::Error,
+        "warning" => Severity::Warning,
+        _ => Severity::Info,
+    }
+}
+
+fn str_at<'a>(value: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .try_fold(value, |value, key| value.get(key))?
+        .as_str()
+}
+
+/// The driver rule a result refers to
+fn rule_of<'a>(result: &Value, rules: &'a [Value]) -> Option<&'a Value> {
+    let index = result
+        .get("ruleIndex")
+        .or_else(|| result.pointer("/rule/index"))
+        .and_then(Value::as_u64);
+    if let Some(rule) = index.and_then(|i| rules.get(i as usize)) {
+        return Some(rule);
+    }
+    let id = result
+        .get("ruleId")
+        .and_then(Value::as_str)
+        .or_else(|| str_at(result, &["rule", "id"]))?;
+    rules
+        .iter()
+        .find(|r| r.get("id").and_then(Value::as_str) == Some(id))
+}
+
+/// `message.text`, or the rule's message string `message.id` names
+fn message(result: &Value, rule: Option<&Value>) -> Option<String> {
+    let message = result.get("message")?;
+    if let Some(text) = message.get("text").and_then(Value::as_str) {
+        return Some(text.to_string());
+    }
+    let id = message.get("id").and_then(Value::as_str)?;
+    rule?
+        .get("messageStrings")?
+        .get(id)?
+        .get("text")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Location URI with its `uriBaseId` expanded when the run defines it
+fn artifact_uri(location: &Value, run: &Value) -> Option<String> {
+    let artifact = location.get("artifactLocation")?;
+    let uri = artifact.get("uri").and_then(Value::as_str)?;
+    let base = artifact
+        .get("uriBaseId")
+        .and_then(Value::as_str)
+        .and_then(|id| run.get("originalUriBaseIds")?.get(id)?.get("uri")?.as_str());
+    Some(match base {
+        Some(base) if !uri.contains("://") => {
+            format!("{}/{}", base.trim_end_matches('/'), uri)
+        }
+        _ => uri.to_string(),
+    })
+}
+
+/// Suppressed in the report (`suppressions` other than rejected ones)
+fn is_suppressed(result: &Value) -> bool {
+    result
+        .get("suppressions")
+        .and_then(Value::as_array)
+        .is_some_and(|suppressions| {
+            suppressions.iter().any(|s| {
+                !matches!(
+                    s.get("status").and_then(Value::as_str),
+                    Some("rejected" | "underReview")
+                )
+            })
+        })
+}
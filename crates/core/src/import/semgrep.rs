@@ -0,0 +1,79 @@
+//! Semgrep JSON reports (`semgrep --json`)
+//!
+//! Results map to `EXT-semgrep-<check_id>`. Both severity scales Semgrep
+//! uses are understood: `ERROR`/`WARNING`/`INFO` and, for Semgrep
+//! Supply Chain and Pro rules, `CRITICAL`/`HIGH`/`MEDIUM`/`LOW`.
+
+use super::{finding, ImportOptions};
+use crate::finding::{Finding, Severity};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Report {
+    results: Vec<SemgrepResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SemgrepResult {
+    check_id: String,
+    path: String,
+    start: Position,
+    #[serde(default)]
+    end: Option<Position>,
+    #[serde(default)]
+    extra: Extra,
+}
+
+#[derive(Debug, Deserialize)]
+struct Position {
+    line: usize,
+    #[serde(default)]
+    col: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Extra {
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    severity: String,
+}
+
+/// Findings in a Semgrep JSON report
+pub fn parse(content: &str, options: &ImportOptions) -> Result<Vec<Finding>> {
+    let report: Report = serde_json::from_str(content).context("Not a Semgrep JSON report")?;
+    let findings = report
+        .results
+        .into_iter()
+        .map(|result| {
+            let message = match result.extra.message.trim() {
+                "" => result.check_id.clone(),
+                message => message.to_string(),
+            };
+            let mut finding = finding(
+                "semgrep",
+                &result.check_id,
+                severity(&result.extra.severity),
+                message,
+                options.resolve(&result.path),
+                result.start.line,
+            );
+            finding.column = result.start.col;
+            finding.end_column = result
+                .end
+                .filter(|end| end.line == result.start.line)
+                .and_then(|end| end.col);
+            finding
+        })
+        .collect();
+    Ok(findings)
+}
+
+fn severity(severity: &str) -> Severity {
+    match severity.to_ascii_uppercase().as_str() {
+        "ERROR" | "CRITICAL" | "HIGH" => Severity::Error,
+        "WARNING" | "MEDIUM" => Severity::Warning,
+        _ => Severity::Info,
+    }
+}
@@ -0,0 +1,546 @@
+//! Language version targets — the language level a project declares
+//!
+//! Analyzers ask [`LanguageTargets`] which version of a language a file is
+//! written for, so suggestions only propose syntax and APIs available at
+//! that version and version-dependent rules apply to the versions the
+//! project supports. A file's target comes from the nearest manifest at or
+//! above its directory, up to the repository root:
+//!
+//! - ECMAScript: `tsconfig.json` / `jsconfig.json` `compilerOptions.target`,
+//!   else the newest edition in `compilerOptions.lib`; a relative `extends`
+//!   is followed
+//! - Node.js: the lower bound of `package.json` `engines.node`
+//! - Python: the lower bound of `pyproject.toml` `project.requires-python`
+//!   or `tool.poetry.dependencies.python`
+//! - Rust: `Cargo.toml` `rust-version` (package or workspace), else the
+//!   first release of its `edition`
+//! - Java: `pom.xml` `maven.compiler.release` / `release` /
+//!   `maven.compiler.source` / `source` / `java.version`; `build.gradle(.kts)`
+//!   `JavaLanguageVersion.of(..)`, `sourceCompatibility` or `options.release`
+//!
+//! `[targets]` in `.revet.toml` sets a language's target for the whole
+//! repository. A language nothing declares — or whose nearest declaration
+//! cannot be read — gets a conservative modern default
+//! ([`Language::default_version`]). Each run records how every language
+//! was resolved at the repository root in its reproducibility block
+//! ([`LanguageTargets::records`]).
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::analyzer::skew::strip_jsonc;
+use crate::analyzer::upgrade::pack::Version;
+
+/// Newest ECMAScript edition `esnext` stands for
+const LATEST_EDITION: &str = "2024";
+
+/// How many `extends` hops of a `tsconfig.json` are followed
+const MAX_EXTENDS: usize = 4;
+
+/// A language whose version analyzers take into account
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Language {
+    /// JavaScript and TypeScript syntax, by ECMAScript edition (`2020`)
+    EcmaScript,
+    /// The Node.js runtime, by release (`20`)
+    Node,
+    Python,
+    Rust,
+    /// Java, by feature release (`8`, `17`)
+    Java,
+}
+
+impl Language {
+    /// Every language, in the order runs record them
+    pub const ALL: [Language; 5] = [
+        Self::EcmaScript,
+        Self::Node,
+        Self::Python,
+        Self::Rust,
+        Self::Java,
+    ];
+
+    /// `[targets]` key
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::EcmaScript => "ecmascript",
+            Self::Node => "node",
+            Self::Python => "python",
+            Self::Rust => "rust",
+            Self::Java => "java",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.key() == key)
+    }
+
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::EcmaScript => "ECMAScript",
+            Self::Node => "Node.js",
+            Self::Python => "Python",
+            Self::Rust => "Rust",
+            Self::Java => "Java",
+        }
+    }
+
+    /// The target assumed when nothing is declared: a recent release that
+    /// maintained toolchains all support
+    pub fn default_version(self) -> Version {
+        let version = match self {
+            Self::EcmaScript => "2020",
+            Self::Node => "20",
+            Self::Python => "3.12",
+            Self::Rust => "1.70",
+            Self::Java => "17",
+        };
+        Version::parse(version).expect("default targets are versions")
+    }
+
+    /// Parse a version as the language's manifests write it: `ES2018` or
+    /// `esnext` for ECMAScript, `1.8` or `VERSION_1_8` for Java 8
+    pub fn parse_version(self, text: &str) -> Option<Version> {
+        match self {
+            Self::EcmaScript => es_edition(text),
+            Self::Java => java_release(text),
+            _ => Version::parse(text),
+        }
+    }
+}
+
+/// Where a target came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetSource {
+    /// `[targets]` in `.revet.toml`
+    Config,
+    /// A manifest value, with the manifest and the key it was read from
+    Manifest { file: PathBuf, key: String },
+    /// Nothing declared it; `unparsable` is the nearest manifest whose
+    /// declaration could not be read
+    Default { unparsable: Option<PathBuf> },
+}
+
+/// A language's target version for one file
+#[derive(Debug, Clone)]
+pub struct LanguageTarget {
+    pub language: Language,
+    pub version: Version,
+    pub source: TargetSource,
+}
+
+impl LanguageTarget {
+    fn default_for(language: Language, unparsable: Option<PathBuf>) -> Self {
+        Self {
+            language,
+            version: language.default_version(),
+            source: TargetSource::Default { unparsable },
+        }
+    }
+
+    /// Whether the target is `version` (as [`Language::parse_version`]
+    /// reads it) or later
+    pub fn at_least(&self, version: &str) -> bool {
+        self.language
+            .parse_version(version)
+            .is_some_and(|v| self.version >= v)
+    }
+
+    /// Whether `[targets]` or a manifest declared the target
+    pub fn is_declared(&self) -> bool {
+        !matches!(self.source, TargetSource::Default { .. })
+    }
+}
+
+/// How a language's target was resolved for a run, as its reproducibility
+/// block records it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TargetRecord {
+    /// `[targets]` key of the language
+    pub language: String,
+    pub version: String,
+    /// `config`, `manifest` or `default`
+    pub source: String,
+    /// The manifest read, relative to the repository root; for `default`,
+    /// the one whose declaration could not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<String>,
+    /// Key of the manifest value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+/// Resolves each file's targets, reading every manifest at most once
+pub struct LanguageTargets<'a> {
+    root: PathBuf,
+    configured: &'a BTreeMap<String, String>,
+    /// Nearest readable or unreadable declaration at or above a directory
+    nearest: HashMap<(Language, PathBuf), Option<LanguageTarget>>,
+}
+
+impl<'a> LanguageTargets<'a> {
+    /// Targets of the files under `root`, with `configured` (`[targets]`)
+    /// taking precedence over manifests
+    pub fn new(root: &Path, configured: &'a BTreeMap<String, String>) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            configured,
+            nearest: HashMap::new(),
+        }
+    }
+
+    /// `language`'s target for `file`: configured, declared by the nearest
+    /// manifest, or the default
+    pub fn for_file(&mut self, language: Language, file: &Path) -> LanguageTarget {
+        let file = if file.is_relative() {
+            self.root.join(file)
+        } else {
+            file.to_path_buf()
+        };
+        let dir = file.parent().unwrap_or(&self.root).to_path_buf();
+        self.resolve(language, &dir)
+    }
+
+    /// Like [`for_file`](Self::for_file), `None` when the target is a
+    /// default rather than declared
+    pub fn declared(&mut self, language: Language, file: &Path) -> Option<LanguageTarget> {
+        Some(self.for_file(language, file)).filter(LanguageTarget::is_declared)
+    }
+
+    /// `language`'s target for the repository as a whole: configured, or
+    /// declared by a manifest at the root
+    pub fn for_repo(&mut self, language: Language) -> LanguageTarget {
+        let root = self.root.clone();
+        self.resolve(language, &root)
+    }
+
+    /// Every language's repository-wide target, for a run's
+    /// reproducibility block
+    pub fn records(&mut self) -> Vec<TargetRecord> {
+        Language::ALL
+            .into_iter()
+            .map(|language| {
+                let target = self.for_repo(language);
+                let relative = |file: &Path| {
+                    file.strip_prefix(&self.root)
+                        .unwrap_or(file)
+                        .to_string_lossy()
+                        .replace('\\', "/")
+                };
+                let (source, manifest, key) = match &target.source {
+                    TargetSource::Config => ("config", None, None),
+                    TargetSource::Manifest { file, key } => {
+                        ("manifest", Some(relative(file)), Some(key.clone()))
+                    }
+                    TargetSource::Default { unparsable } => {
+                        ("default", unparsable.as_deref().map(relative), None)
+                    }
+                };
+                TargetRecord {
+                    language: language.key().to_string(),
+                    version: target.version.to_string(),
+                    source: source.to_string(),
+                    manifest,
+                    key,
+                }
+            })
+            .collect()
+    }
+
+    fn resolve(&mut self, language: Language, dir: &Path) -> LanguageTarget {
+        if let Some(version) = self
+            .configured
+            .get(language.key())
+            .and_then(|v| language.parse_version(v))
+        {
+            return LanguageTarget {
+                language,
+                version,
+                source: TargetSource::Config,
+            };
+        }
+        self.nearest(language, dir)
+            .unwrap_or_else(|| LanguageTarget::default_for(language, None))
+    }
+
+    /// The first declaration found from `dir` up to the repository root
+    fn nearest(&mut self, language: Language, dir: &Path) -> Option<LanguageTarget> {
+        let key = (language, dir.to_path_buf());
+        if let Some(cached) = self.nearest.get(&key) {
+            return cached.clone();
+        }
+        let target = match declaration(language, dir) {
+            Declaration::Found(target) => Some(target),
+            Declaration::Unparsable(file) => {
+                Some(LanguageTarget::default_for(language, Some(file)))
+            }
+            Declaration::None if dir == self.root || !dir.starts_with(&self.root) => None,
+            Declaration::None => dir
+                .parent()
+                .and_then(|parent| self.nearest(language, parent)),
+        };
+        self.nearest.insert(key, target.clone());
+        target
+    }
+}
+
+// ── Reading manifests ─────────────────────────────────────────────────────────
+
+/// What the manifests of one directory say about a language
+enum Declaration {
+    Found(LanguageTarget),
+    /// A manifest declares a value that could not be read
+    Unparsable(PathBuf),
+    None,
+}
+
+fn found(language: Language, version: Version, file: &Path, key: &str) -> Declaration {
+    Declaration::Found(LanguageTarget {
+        language,
+        version,
+        source: TargetSource::Manifest {
+            file: file.to_path_buf(),
+            key: key.to_string(),
+        },
+    })
+}
+
+/// `language`'s declaration in the manifests directly in `dir`
+fn declaration(language: Language, dir: &Path) -> Declaration {
+    let manifests: &[&str] = match language {
+        Language::EcmaScript => &["tsconfig.json", "jsconfig.json"],
+        Language::Node => &["package.json"],
+        Language::Python => &["pyproject.toml"],
+        Language::Rust => &["Cargo.toml"],
+        Language::Java => &["pom.xml", "build.gradle", "build.gradle.kts"],
+    };
+    for name in manifests {
+        let file = dir.join(name);
+        let Ok(content) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        let declared = match language {
+            Language::EcmaScript => tsconfig(&file, &content, 0),
+            Language::Node => range(language, &file, &content, &["engines.node"]),
+            Language::Python => range(
+                language,
+                &file,
+                &content,
+                &["project.requires-python", "tool.poetry.dependencies.python"],
+            ),
+            Language::Rust => cargo(&file, &content),
+            Language::Java if *name == "pom.xml" => pom(&file, &content),
+            Language::Java => gradle(&file, &content),
+        };
+        if !matches!(declared, Declaration::None) {
+            return declared;
+        }
+    }
+    Declaration::None
+}
+
+/// A `.json` (comments and trailing commas allowed) or `.toml` document
+fn document(file: &Path, content: &str) -> Option<Value> {
+    if file.extension().is_some_and(|e| e == "json") {
+        serde_json::from_str(&strip_jsonc(content)).ok()
+    } else {
+        let doc: toml::Value = toml::from_str(content).ok()?;
+        serde_json::to_value(doc).ok()
+    }
+}
+
+fn value_at<'v>(doc: &'v Value, key: &str) -> Option<&'v Value> {
+    key.split('.').try_fold(doc, |value, part| value.get(part))
+}
+
+/// The lower bound of the first version range found at `keys`
+fn range(language: Language, file: &Path, content: &str, keys: &[&str]) -> Declaration {
+    let Some(doc) = document(file, content) else {
+        return Declaration::Unparsable(file.to_path_buf());
+    };
+    for key in keys {
+        let Some(range) = value_at(&doc, key).and_then(Value::as_str) else {
+            continue;
+        };
+        return match Version::lower_bound(range) {
+            Some(version) => found(language, version, file, key),
+            None => Declaration::Unparsable(file.to_path_buf()),
+        };
+    }
+    Declaration::None
+}
+
+/// `compilerOptions.target`, else the newest `compilerOptions.lib`
+/// edition, else what a relative `extends` declares
+fn tsconfig(file: &Path, content: &str, depth: usize) -> Declaration {
+    let language = Language::EcmaScript;
+    let Some(doc) = document(file, content) else {
+        return Declaration::Unparsable(file.to_path_buf());
+    };
+    if let Some(target) = value_at(&doc, "compilerOptions.target").and_then(Value::as_str) {
+        return match es_edition(target) {
+            Some(version) => found(language, version, file, "compilerOptions.target"),
+            None => Declaration::Unparsable(file.to_path_buf()),
+        };
+    }
+    let newest_lib = value_at(&doc, "compilerOptions.lib")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter_map(|lib| es_edition(lib.split('.').next().unwrap_or(lib)))
+        .max();
+    if let Some(version) = newest_lib {
+        return found(language, version, file, "compilerOptions.lib");
+    }
+    let base = doc
+        .get("extends")
+        .and_then(Value::as_str)
+        .filter(|base| base.starts_with('.') && depth < MAX_EXTENDS);
+    let Some(base) = base else {
+        return Declaration::None;
+    };
+    let base = if base.ends_with(".json") {
+        base.to_string()
+    } else {
+        format!("{}.json", base)
+    };
+    let base = file.parent().unwrap_or(Path::new("")).join(base);
+    match std::fs::read_to_string(&base) {
+        Ok(content) => tsconfig(&base, &content, depth + 1),
+        Err(_) => Declaration::Unparsable(file.to_path_buf()),
+    }
+}
+
+/// `rust-version`, else the first release of the `edition`
+fn cargo(file: &Path, content: &str) -> Declaration {
+    let language = Language::Rust;
+    let Some(doc) = document(file, content) else {
+        return Declaration::Unparsable(file.to_path_buf());
+    };
+    // `rust-version.workspace = true` is not a string and falls through to
+    // the edition
+    for key in ["package.rust-version", "workspace.package.rust-version"] {
+        if let Some(text) = value_at(&doc, key).and_then(Value::as_str) {
+            return match Version::parse(text) {
+                Some(version) => found(language, version, file, key),
+                None => Declaration::Unparsable(file.to_path_buf()),
+            };
+        }
+    }
+    for key in ["package.edition", "workspace.package.edition"] {
+        if let Some(edition) = value_at(&doc, key).and_then(Value::as_str) {
+            let first_release = match edition {
+                "2015" => "1.0",
+                "2018" => "1.31",
+                "2021" => "1.56",
+                "2024" => "1.85",
+                _ => return Declaration::Unparsable(file.to_path_buf()),
+            };
+            let version = Version::parse(first_release).expect("editions map to versions");
+            return found(language, version, file, key);
+        }
+    }
+    Declaration::None
+}
+
+/// The first of the compiler properties a `pom.xml` sets; a `${property}`
+/// value is looked up once
+fn pom(file: &Path, content: &str) -> Declaration {
+    const TAGS: &[&str] = &[
+        "maven.compiler.release",
+        "release",
+        "maven.compiler.source",
+        "source",
+        "java.version",
+    ];
+    let tag_value = |tag: &str| {
+        let open = format!("<{}>", tag);
+        let start = content.find(&open)? + open.len();
+        let end = start + content[start..].find('<')?;
+        Some(content[start..end].trim().to_string())
+    };
+    for tag in TAGS {
+        let Some(mut value) = tag_value(tag) else {
+            continue;
+        };
+        if let Some(property) = value.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
+            value = tag_value(property).unwrap_or_default();
+        }
+        return match java_release(&value) {
+            Some(version) => found(Language::Java, version, file, tag),
+            None => Declaration::Unparsable(file.to_path_buf()),
+        };
+    }
+    Declaration::None
+}
+
+/// A Gradle toolchain's `languageVersion`, else `sourceCompatibility` or
+/// `options.release`
+fn gradle(file: &Path, content: &str) -> Declaration {
+    static PATTERNS: OnceLock<[(&str, Regex); 3]> = OnceLock::new();
+    let patterns = PATTERNS.get_or_init(|| {
+        [
+            (
+                "languageVersion",
+                Regex::new(r"JavaLanguageVersion\.of\(\s*([\w.]+)\s*\)").unwrap(),
+            ),
+            (
+                "sourceCompatibility",
+                Regex::new(
+                    r#"sourceCompatibility\s*(?:=|\.set\()?\s*(?:JavaVersion\.(?:VERSION_)?)?["']?([\w.]+)"#,
+                )
+                .unwrap(),
+            ),
+            (
+                "options.release",
+                Regex::new(r"options\.release\s*(?:=|\.set\()\s*([\w.]+)").unwrap(),
+            ),
+        ]
+    });
+    for (key, pattern) in patterns {
+        if let Some(caps) = pattern.captures(content) {
+            return match java_release(&caps[1]) {
+                Some(version) => found(Language::Java, version, file, key),
+                None => Declaration::Unparsable(file.to_path_buf()),
+            };
+        }
+    }
+    Declaration::None
+}
+
+/// `ES2018`, `es6`, `ES5`, `esnext` (or a bare `2018`)
+fn es_edition(text: &str) -> Option<Version> {
+    let text = text.trim().to_ascii_lowercase();
+    let edition = text.strip_prefix("es").unwrap_or(&text);
+    let year = match edition {
+        "next" => LATEST_EDITION,
+        "3" | "5" => edition,
+        "6" => "2015",
+        year if year.len() == 4 && year.as_bytes().iter().all(u8::is_ascii_digit) => year,
+        _ => return None,
+    };
+    Version::parse(year)
+}
+
+/// `17`, `1.8`, `VERSION_1_8`, `JavaVersion.VERSION_17`: the feature
+/// release, with the `1.` of Java 8 and earlier dropped
+fn java_release(text: &str) -> Option<Version> {
+    let text = text.trim().trim_matches(['"', '\'']);
+    let text = text
+        .rsplit("VERSION_")
+        .next()
+        .unwrap_or(text)
+        .replace('_', ".");
+    let text = match text.strip_prefix("1.") {
+        Some(rest) if !rest.is_empty() => rest.to_string(),
+        _ => text,
+    };
+    Version::parse(&text)
+}
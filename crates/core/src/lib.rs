@@ -6,44 +6,126 @@
 //! - Git diff analysis and cross-file impact detection
 //! - Graph caching for incremental analysis
 
+pub mod ai;
+#[cfg(feature = "alloc-stats")]
+pub mod alloc_stats;
 pub mod analyzer;
+pub mod audit;
+pub mod authorship;
 pub mod baseline;
+pub mod buffer;
 pub mod cache;
+pub mod calibrate;
+pub mod cancel;
+pub mod case;
+pub mod churn;
+pub mod code_kind;
 pub mod config;
+pub mod coverage;
 pub mod diff;
 pub mod discovery;
 pub mod finding;
 pub mod fixer;
+pub mod generated;
+pub mod git_objects;
 pub mod graph;
+pub mod hash_inputs;
+pub mod i18n;
+pub mod import;
+pub mod language_target;
+pub mod line_map;
+pub mod lockfile;
+pub mod lsp;
+pub mod memory;
 pub mod parser;
+pub mod pipeline;
+pub mod policy;
+pub mod profile;
+pub mod redact;
+pub mod rollout;
+pub mod sbom;
+pub mod schedule;
+pub mod session_ignore;
+pub mod storage;
 pub mod store;
+pub mod suggestion;
 pub mod suppress;
+pub mod trace;
+pub mod vendored;
+pub mod workspace;
 
 pub use analyzer::{
-    toolchain::ToolchainAnalyzer, Analyzer, AnalyzerDispatcher, AnalyzerTiming, GraphAnalyzer,
+    file_timing::TimingReport, toolchain::ToolchainAnalyzer, Analyzer, AnalyzerDispatcher,
+    AnalyzerTiming, GraphAnalyzer,
 };
-pub use baseline::{filter_findings, Baseline, BaselineEntry};
-pub use cache::{FileGraphCache, GraphCache, GraphCacheMeta};
-pub use config::{GateConfig, RevetConfig};
+pub use audit::MutationJournal;
+pub use baseline::{
+    entry_statuses, filter_findings, Baseline, BaselineEntry, EntryMatch, EntryStatus,
+};
+pub use buffer::{analyze_buffer, GraphContext};
+pub use cache::memo::{AnalysisMemo, MemoStats};
+pub use cache::remote::{RemoteCache, RemoteStats, RemoteStore};
+pub use cache::{AnalysisCache, CacheFormat, FileGraphCache, GraphCache, GraphCacheMeta};
+pub use calibrate::{Budget, Metric};
+pub use cancel::{CancelToken, Cancelled};
+pub use code_kind::{CodeKind, CodeKinds};
+pub use config::{
+    CacheConfig, CalibrationConfig, ConcurrencyConfig, ConfigFile, CouplingConfig, DocsConfig,
+    FilesConfig, GateConfig, GitConfig, IssuesConfig, PerformanceConfig, PolicyConfig,
+    ProfileConfig, ProjectConfig, ReportingConfig, RevetConfig, ReviewEstimateConfig, RiskConfig,
+    SeverityOverride, VendoredConfig,
+};
+pub use coverage::{CoverageLedger, CoverageReport, CoverageSummary, SkipReason};
 pub use diff::{
     filter_findings_by_diff, BlastRadiusSummary, ChangeClassification, ChangeImpact, DiffAnalyzer,
-    DiffFileLines, DiffLineMap, GitTreeReader, ImpactAnalysis, ImpactSummary, RiskLevel,
+    DiffFileLines, DiffLineMap, GitTreeReader, ImpactAnalysis, ImpactSummary, ReviewEstimate,
+    RiskLevel, RiskReport,
+};
+pub use discovery::{
+    discover_files, discover_files_extended, discover_files_recorded, discover_files_with_dotfiles,
+    is_dotfile, matches_dotfile,
+};
+pub use finding::{
+    Confidence, Finding, FindingDetails, FindingLocation, FixKind, InsertContext, ReviewSummary,
+    Severity, SuggestedCode, VersionRow,
 };
-pub use discovery::{discover_files, discover_files_extended};
-pub use finding::{Finding, FixKind, ReviewSummary, Severity};
-pub use fixer::{apply_fixes, FixReport};
+pub use fixer::{apply_fixes, Anchor, DroppedAnchor, FixReport};
+pub use generated::{GeneratedFiles, GeneratedPolicy, GeneratedSet};
+pub use git_objects::{CloneLayout, Degradation, Feature, GitObjects, MissingBlob, SparseCheckout};
 pub use graph::{
-    CodeGraph, Edge, EdgeKind, EdgeMetadata, MergeMap, Node, NodeData, NodeId, NodeKind,
+    CodeGraph, Edge, EdgeKind, EdgeMetadata, ImportKind, MergeMap, Node, NodeData, NodeFingerprint,
+    NodeId, NodeKind,
 };
+pub use hash_inputs::{HashInputs, Project};
+pub use i18n::{Locale, Msg};
+pub use language_target::{Language, LanguageTarget, LanguageTargets, TargetRecord, TargetSource};
+pub use line_map::LineMap;
+pub use lockfile::{Ecosystem, LockedPackage};
+pub use memory::{MemoryGuard, MemoryReport, Shedding};
+pub use parser::incremental::{IncrementalGraph, Update as GraphUpdate};
 pub use parser::{LanguageParser, ParseError, ParseState, ParserDispatcher, UnresolvedImport};
+pub use pipeline::{analyze_root, Analysis};
+pub use policy::{Policy, PolicyReport, PolicyResult};
+pub use profile::{ProfileSettings, Reproducibility, Scope};
+pub use redact::{mask_secret, redact_spans, strip_masks, SecretIndex};
+pub use rollout::{Rollout, RolloutDecision, ShadowModule};
+pub use sbom::{Sbom, SbomFormat};
+pub use schedule::{BudgetReport, Scheduler, Timings};
+pub use session_ignore::{SessionIgnore, SessionIgnores};
+pub use storage::StoragePaths;
 pub use store::{reconstruct_graph, GraphStore, MemoryStore, StoreNodeId};
 pub use suppress::{
-    filter_findings_by_inline, filter_findings_by_path_rules, matches_suppression,
-    parse_suppressions, SuppressedFinding,
+    filter_findings_by_inline, filter_findings_by_path_rules, filter_inline, filter_inline_with,
+    matches_finding, matches_suppression, parse_directives, parse_suppressions,
+    parse_suppressions_at, resolve_suppressions, Constructs, Directive, DirectiveDefect,
+    DirectiveUsage, FilterOutcome, Misplaced, Placement, RuleOptions, SuppressedFinding,
+    Suppression,
 };
+pub use vendored::{VendoredFiles, VendoredOverlay, VendoredSet};
+pub use workspace::{WorkspaceConfig, WorkspaceRepo, WORKSPACE_FILE};
 
 #[cfg(feature = "cozo-store")]
-pub use store::{create_store, CozoStore};
+pub use store::{create_store, create_store_at, open_store, CozoStore};
 
 /// Revet version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
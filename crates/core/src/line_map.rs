@@ -0,0 +1,88 @@
+//! Line mapping across edits — where each line of a file went after a set of
+//! edits was applied to it
+//!
+//! Anything that stores a line number (findings, the last-run log, baseline
+//! anchors) goes stale when a fix rewrites the file. A [`LineMap`] built from
+//! the edit spans maps each original line to its new number, or to `None`
+//! when the edit deleted it:
+//!
+//! - a deleted line maps to `None`, and the lines after it move up by one
+//! - an edit whose new text has `k` line breaks moves the lines after it
+//!   down by `k`; the edited line itself moves too when the edit starts at
+//!   its first column, since its text now follows the inserted lines
+//! - every other line keeps its text and moves by the edits above it
+
+use crate::fixer::FixPlan;
+use std::collections::BTreeMap;
+
+/// One edit span, as far as line numbers are concerned; [`LineMap`] keys it
+/// by the line it starts on
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LineEdit {
+    /// The edit starts at the line's first column
+    at_start: bool,
+    /// Line breaks in the new text
+    inserted: usize,
+    /// The whole line was deleted with its line break
+    removes_line: bool,
+}
+
+/// Mapping from the lines of a file before a set of edits to its lines after
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineMap {
+    edits: BTreeMap<usize, Vec<LineEdit>>,
+}
+
+impl LineMap {
+    /// The mapping for `plans`, non-overlapping edits planned against the
+    /// same text (see [`plan_fixes`](crate::fixer::plan_fixes))
+    pub fn from_plans<'a>(plans: impl IntoIterator<Item = &'a FixPlan>) -> Self {
+        let mut map = Self::default();
+        for plan in plans {
+            map.edits.entry(plan.line).or_default().push(LineEdit {
+                at_start: plan.start_column <= 1,
+                inserted: plan.new_text.matches('\n').count(),
+                removes_line: plan.removes_line,
+            });
+        }
+        map
+    }
+
+    /// Whether no edit changes any line number
+    pub fn is_identity(&self) -> bool {
+        self.edits
+            .values()
+            .flatten()
+            .all(|e| e.inserted == 0 && !e.removes_line)
+    }
+
+    /// Whether the edits deleted `line`
+    pub fn is_deleted(&self, line: usize) -> bool {
+        self.edits
+            .get(&line)
+            .is_some_and(|edits| edits.iter().any(|e| e.removes_line))
+    }
+
+    /// New number of the 1-based `line`, `None` if it was deleted
+    pub fn map(&self, line: usize) -> Option<usize> {
+        if self.is_deleted(line) {
+            return None;
+        }
+        let mut shift: isize = 0;
+        for edit in self.edits.range(..line).flat_map(|(_, e)| e) {
+            shift += if edit.removes_line {
+                -1
+            } else {
+                edit.inserted as isize
+            };
+        }
+        if let Some(edits) = self.edits.get(&line) {
+            shift += edits
+                .iter()
+                .filter(|e| e.at_start)
+                .map(|e| e.inserted as isize)
+                .sum::<isize>();
+        }
+        Some(line.saturating_add_signed(shift))
+    }
+}
@@ -0,0 +1,69 @@
+//! Offline vulnerability advisories
+//!
+//! An advisory file is a JSON array of entries naming an affected package and
+//! the exact versions it affects, so matching needs no network access:
+//!
+//! ```json
+//! [
+//!   {
+//!     "id": "GHSA-jf85-cpcp-j695",
+//!     "ecosystem": "npm",
+//!     "package": "lodash",
+//!     "versions": ["4.17.11", "4.17.15"],
+//!     "severity": "high",
+//!     "summary": "Prototype pollution in lodash",
+//!     "url": "https://github.com/advisories/GHSA-jf85-cpcp-j695"
+//!   }
+//! ]
+//! ```
+
+use super::{Ecosystem, LockedPackage};
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+/// A known vulnerability affecting specific package versions
+#[derive(Debug, Clone, Deserialize)]
+pub struct Advisory {
+    pub id: String,
+    pub ecosystem: String,
+    pub package: String,
+    #[serde(default)]
+    pub versions: Vec<String>,
+    /// `critical`, `high`, `medium`, `low` (anything else is reported as unknown)
+    #[serde(default)]
+    pub severity: Option<String>,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+impl Advisory {
+    /// Whether this advisory affects `package`
+    pub fn matches(&self, package: &LockedPackage) -> bool {
+        Ecosystem::parse(&self.ecosystem) == Some(package.ecosystem)
+            && package.ecosystem.normalize_name(&self.package)
+                == package.ecosystem.normalize_name(&package.name)
+            && self.versions.iter().any(|v| v == &package.version)
+    }
+}
+
+/// Load an advisory file
+pub fn load_advisories(path: &Path) -> Result<Vec<Advisory>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read advisory file {}", path.display()))?;
+    let advisories: Vec<Advisory> = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse advisory file {}", path.display()))?;
+    if let Some(bad) = advisories
+        .iter()
+        .find(|a| Ecosystem::parse(&a.ecosystem).is_none())
+    {
+        return Err(anyhow!(
+            "Advisory {} has unknown ecosystem {:?} (expected npm, cargo, pypi or go)",
+            bad.id,
+            bad.ecosystem
+        ));
+    }
+    Ok(advisories)
+}
@@ -0,0 +1,84 @@
+//! Cargo `Cargo.lock` parsing
+//!
+//! Packages without a `source` are the workspace's own crates (or path
+//! dependencies) and are not components. A registry or git package is direct
+//! when one of those local crates lists it in its `dependencies`.
+
+use super::{Ecosystem, ParsedLockfile, ParsedPackage};
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+#[derive(Deserialize)]
+struct CargoLock {
+    #[serde(default)]
+    package: Vec<LockEntry>,
+}
+
+#[derive(Deserialize)]
+struct LockEntry {
+    name: String,
+    version: String,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+/// Parse a `Cargo.lock`; `dir` is the directory containing it
+pub fn parse(content: &str, dir: &Path) -> Result<ParsedLockfile> {
+    let lock: CargoLock = toml::from_str(content)?;
+
+    // Dependency specs are "name", "name version" or "name version (source)"
+    let mut direct_names = HashSet::new();
+    let mut direct_versions = HashSet::new();
+    for entry in lock.package.iter().filter(|p| p.source.is_none()) {
+        for dep in &entry.dependencies {
+            let mut parts = dep.split_whitespace();
+            let Some(name) = parts.next() else { continue };
+            match parts.next() {
+                Some(version) => {
+                    direct_versions.insert((name.to_string(), version.to_string()));
+                }
+                None => {
+                    direct_names.insert(name.to_string());
+                }
+            }
+        }
+    }
+
+    let packages = lock
+        .package
+        .into_iter()
+        .filter(|p| p.source.is_some())
+        .map(|p| {
+            let direct = direct_names.contains(&p.name)
+                || direct_versions.contains(&(p.name.clone(), p.version.clone()));
+            ParsedPackage {
+                ecosystem: Ecosystem::Cargo,
+                name: p.name,
+                version: p.version,
+                direct,
+            }
+        })
+        .collect();
+
+    Ok(ParsedLockfile {
+        project: project_name(dir),
+        packages,
+    })
+}
+
+/// `[package].name` from the sibling `Cargo.toml` (absent for virtual workspaces)
+fn project_name(dir: &Path) -> Option<String> {
+    let manifest: toml::Value = std::fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()?
+        .parse()
+        .ok()?;
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(String::from)
+}
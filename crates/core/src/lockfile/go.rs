@@ -0,0 +1,58 @@
+//! Go `go.mod` parsing
+//!
+//! Since Go 1.17 `go.mod` lists every module in the build list, with
+//! `// indirect` marking those not imported by the main module directly, so
+//! it serves as the lockfile (`go.sum` only adds hashes).
+
+use super::{Ecosystem, ParsedLockfile, ParsedPackage};
+
+/// Parse a `go.mod`
+pub fn parse(content: &str) -> ParsedLockfile {
+    let mut project = None;
+    let mut packages = Vec::new();
+    let mut in_require = false;
+
+    for raw in content.lines() {
+        let (code, comment) = match raw.find("//") {
+            Some(idx) => (&raw[..idx], &raw[idx + 2..]),
+            None => (raw, ""),
+        };
+        let code = code.trim();
+        let indirect = comment.trim() == "indirect" || comment.trim().starts_with("indirect;");
+
+        if in_require {
+            if code == ")" {
+                in_require = false;
+            } else if let Some(pkg) = requirement(code, indirect) {
+                packages.push(pkg);
+            }
+            continue;
+        }
+
+        if let Some(rest) = code.strip_prefix("module ") {
+            project = Some(rest.trim().trim_matches('"').to_string());
+        } else if let Some(rest) = code.strip_prefix("require") {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_require = true;
+            } else if let Some(pkg) = requirement(rest, indirect) {
+                packages.push(pkg);
+            }
+        }
+    }
+
+    ParsedLockfile { project, packages }
+}
+
+/// `module/path v1.2.3`
+fn requirement(spec: &str, indirect: bool) -> Option<ParsedPackage> {
+    let mut parts = spec.split_whitespace();
+    let name = parts.next()?.trim_matches('"');
+    let version = parts.next()?;
+    Some(ParsedPackage {
+        ecosystem: Ecosystem::Go,
+        name: name.to_string(),
+        version: version.to_string(),
+        direct: !indirect,
+    })
+}
@@ -0,0 +1,241 @@
+//! Lockfile parsing — resolved dependency sets per project
+//!
+//! Shared by everything that needs the concrete package versions a project
+//! builds against (SBOM generation, advisory matching). Each ecosystem module
+//! turns one lockfile into a list of [`LockedPackage`]s, marking which are
+//! declared directly by the project and which are pulled in transitively.
+//!
+//! Supported inputs:
+//! - npm: `package-lock.json` (lockfile v1–v3)
+//! - Cargo: `Cargo.lock`
+//! - Python: `poetry.lock` (+ `pyproject.toml`), pinned `requirements.txt`
+//! - Go: `go.mod` (`// indirect` marks transitive requirements)
+
+pub mod advisory;
+pub mod cargo;
+pub mod go;
+pub mod npm;
+pub mod python;
+
+use crate::discovery::discover_files_extended;
+use anyhow::Result;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Lockfile names recognised by [`parse_lockfile`]
+pub const LOCKFILE_NAMES: &[&str] = &[
+    "package-lock.json",
+    "Cargo.lock",
+    "poetry.lock",
+    "requirements.txt",
+    "go.mod",
+];
+
+/// Package ecosystem, named after its package-url type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Ecosystem {
+    Npm,
+    Cargo,
+    PyPI,
+    Go,
+}
+
+impl Ecosystem {
+    /// Package-url type (`pkg:<type>/...`)
+    pub fn purl_type(&self) -> &'static str {
+        match self {
+            Ecosystem::Npm => "npm",
+            Ecosystem::Cargo => "cargo",
+            Ecosystem::PyPI => "pypi",
+            Ecosystem::Go => "golang",
+        }
+    }
+
+    /// Parse an ecosystem name as used in advisory files (case-insensitive)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "npm" => Some(Ecosystem::Npm),
+            "cargo" | "crates.io" => Some(Ecosystem::Cargo),
+            "pypi" | "pip" | "python" => Some(Ecosystem::PyPI),
+            "go" | "golang" => Some(Ecosystem::Go),
+            _ => None,
+        }
+    }
+
+    /// Normalize a package name for comparison within this ecosystem
+    ///
+    /// PyPI names are case-insensitive with `-`, `_` and `.` equivalent
+    /// (PEP 503); other ecosystems compare names verbatim.
+    pub fn normalize_name(&self, name: &str) -> String {
+        match self {
+            Ecosystem::PyPI => name.to_ascii_lowercase().replace(['_', '.'], "-"),
+            _ => name.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Ecosystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Ecosystem::Npm => "npm",
+            Ecosystem::Cargo => "cargo",
+            Ecosystem::PyPI => "pypi",
+            Ecosystem::Go => "go",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A resolved package from a lockfile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedPackage {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    pub version: String,
+    /// Declared by the project itself rather than pulled in transitively
+    pub direct: bool,
+    /// Manifest that declares the project's dependencies (repo-relative)
+    pub manifest: PathBuf,
+    /// Name of the project that owns the lockfile
+    pub project: String,
+}
+
+impl LockedPackage {
+    /// Package URL (<https://github.com/package-url/purl-spec>)
+    pub fn purl(&self) -> String {
+        let name = self.ecosystem.normalize_name(&self.name);
+        let path = match self.ecosystem {
+            // Namespaces and names are separate segments, each percent-encoded
+            Ecosystem::Npm | Ecosystem::Go => name
+                .split('/')
+                .map(purl_encode)
+                .collect::<Vec<_>>()
+                .join("/"),
+            _ => purl_encode(&name),
+        };
+        format!(
+            "pkg:{}/{}@{}",
+            self.ecosystem.purl_type(),
+            path,
+            purl_encode(&self.version)
+        )
+    }
+}
+
+/// Percent-encode a purl segment (everything but unreserved characters)
+fn purl_encode(segment: &str) -> String {
+    let mut out = String::with_capacity(segment.len());
+    for b in segment.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{:02X}", b));
+        }
+    }
+    out
+}
+
+/// Parse one lockfile, dispatching on its file name.
+///
+/// `repo_root` is used to make the manifest path repo-relative and to name
+/// projects that don't declare a name. Returns `Ok(None)` for unrecognised
+/// file names.
+pub fn parse_lockfile(path: &Path, repo_root: &Path) -> Result<Option<Vec<LockedPackage>>> {
+    let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+        return Ok(None);
+    };
+    let dir = path.parent().unwrap_or(repo_root);
+    let content = std::fs::read_to_string(path)?;
+    let packages = match file_name {
+        "package-lock.json" => npm::parse(&content, dir)?,
+        "Cargo.lock" => cargo::parse(&content, dir)?,
+        "poetry.lock" => python::parse_poetry(&content, dir)?,
+        "requirements.txt" => python::parse_requirements(&content),
+        "go.mod" => go::parse(&content),
+        _ => return Ok(None),
+    };
+
+    let manifest_name = match file_name {
+        "package-lock.json" => "package.json",
+        "Cargo.lock" => "Cargo.toml",
+        "poetry.lock" => "pyproject.toml",
+        other => other,
+    };
+    let manifest = relative(&dir.join(manifest_name), repo_root);
+    let project = packages
+        .project
+        .unwrap_or_else(|| default_project_name(dir, repo_root));
+
+    Ok(Some(
+        packages
+            .packages
+            .into_iter()
+            .map(|p| LockedPackage {
+                ecosystem: p.ecosystem,
+                name: p.name,
+                version: p.version,
+                direct: p.direct,
+                manifest: manifest.clone(),
+                project: project.clone(),
+            })
+            .collect(),
+    ))
+}
+
+/// Discover and parse every lockfile under `repo_root`.
+///
+/// A `poetry.lock` supersedes a `requirements.txt` in the same directory (the
+/// latter is usually an export of the former). Output order follows the
+/// discovered file order.
+pub fn collect(repo_root: &Path, ignore_patterns: &[String]) -> Result<Vec<LockedPackage>> {
+    let repo_root = repo_root.canonicalize()?;
+    let mut files = discover_files_extended(&repo_root, &[], LOCKFILE_NAMES, ignore_patterns)?;
+    files.sort();
+
+    let mut packages = Vec::new();
+    for file in &files {
+        let is_requirements = file.file_name().is_some_and(|n| n == "requirements.txt");
+        if is_requirements && file.with_file_name("poetry.lock").exists() {
+            continue;
+        }
+        if let Some(found) = parse_lockfile(file, &repo_root)? {
+            packages.extend(found);
+        }
+    }
+    Ok(packages)
+}
+
+/// Packages parsed from one lockfile, before project/manifest are attached
+#[derive(Debug, Default)]
+pub struct ParsedLockfile {
+    /// Project name declared by the manifest, if any
+    pub project: Option<String>,
+    pub packages: Vec<ParsedPackage>,
+}
+
+/// A package as read from a lockfile
+#[derive(Debug, Clone)]
+pub struct ParsedPackage {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    pub version: String,
+    pub direct: bool,
+}
+
+fn relative(path: &Path, repo_root: &Path) -> PathBuf {
+    path.strip_prefix(repo_root).unwrap_or(path).to_path_buf()
+}
+
+/// Project name for lockfiles whose manifest has none: the directory
+/// relative to the repo root, or the repo directory name at the root.
+///
+/// Also names the packages of a monorepo in `SKEW` findings.
+pub(crate) fn default_project_name(dir: &Path, repo_root: &Path) -> String {
+    match dir.strip_prefix(repo_root) {
+        Ok(rel) if !rel.as_os_str().is_empty() => rel.to_string_lossy().replace('\\', "/"),
+        _ => repo_root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "root".to_string()),
+    }
+}
@@ -0,0 +1,121 @@
+//! npm `package-lock.json` parsing
+//!
+//! Lockfile v2/v3 list every installed package under `packages`, keyed by its
+//! `node_modules/...` install path; v1 nests them under `dependencies`. A
+//! package is direct when it is installed at the top level and named in the
+//! dependencies of the root project or one of its workspaces.
+
+use super::{Ecosystem, ParsedLockfile, ParsedPackage};
+use anyhow::Result;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::path::Path;
+
+const DEPENDENCY_FIELDS: &[&str] = &[
+    "dependencies",
+    "devDependencies",
+    "optionalDependencies",
+    "peerDependencies",
+];
+
+/// Parse a `package-lock.json`; `dir` is the directory containing it
+pub fn parse(content: &str, dir: &Path) -> Result<ParsedLockfile> {
+    let lock: Value = serde_json::from_str(content)?;
+    let manifest: Option<Value> = std::fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok());
+
+    let project = manifest
+        .as_ref()
+        .and_then(|m| m.get("name"))
+        .or_else(|| lock.get("name"))
+        .and_then(Value::as_str)
+        .map(String::from);
+
+    let packages = match lock.get("packages").and_then(Value::as_object) {
+        Some(packages) => parse_packages(packages),
+        None => {
+            let mut direct = HashSet::new();
+            if let Some(manifest) = &manifest {
+                collect_dependency_names(manifest, &mut direct);
+            }
+            let mut out = Vec::new();
+            if let Some(deps) = lock.get("dependencies").and_then(Value::as_object) {
+                parse_v1(deps, &direct, true, &mut out);
+            }
+            out
+        }
+    };
+
+    Ok(ParsedLockfile { project, packages })
+}
+
+/// Lockfile v2/v3 `packages` map
+fn parse_packages(packages: &Map<String, Value>) -> Vec<ParsedPackage> {
+    // Dependencies declared by the root ("") and workspace entries
+    let mut direct = HashSet::new();
+    for (key, entry) in packages {
+        if !key.contains("node_modules/") {
+            collect_dependency_names(entry, &mut direct);
+        }
+    }
+
+    let mut out = Vec::new();
+    for (key, entry) in packages {
+        let Some(idx) = key.rfind("node_modules/") else {
+            continue;
+        };
+        if entry.get("link").and_then(Value::as_bool) == Some(true) {
+            continue;
+        }
+        let Some(version) = entry.get("version").and_then(Value::as_str) else {
+            continue;
+        };
+        let name = entry
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or(&key[idx + "node_modules/".len()..])
+            .to_string();
+        let top_level = idx == 0 && !key[idx + "node_modules/".len()..].contains("node_modules/");
+        out.push(ParsedPackage {
+            ecosystem: Ecosystem::Npm,
+            direct: top_level && direct.contains(&name),
+            name,
+            version: version.to_string(),
+        });
+    }
+    out
+}
+
+/// Lockfile v1 nested `dependencies` tree
+fn parse_v1(
+    deps: &Map<String, Value>,
+    direct: &HashSet<String>,
+    top_level: bool,
+    out: &mut Vec<ParsedPackage>,
+) {
+    for (name, entry) in deps {
+        if let Some(version) = entry.get("version").and_then(Value::as_str) {
+            // `file:` / `link:` versions are local packages, not components
+            if !version.starts_with("file:") && !version.starts_with("link:") {
+                out.push(ParsedPackage {
+                    ecosystem: Ecosystem::Npm,
+                    name: name.clone(),
+                    version: version.to_string(),
+                    direct: top_level && direct.contains(name),
+                });
+            }
+        }
+        if let Some(nested) = entry.get("dependencies").and_then(Value::as_object) {
+            parse_v1(nested, direct, false, out);
+        }
+    }
+}
+
+fn collect_dependency_names(manifest: &Value, names: &mut HashSet<String>) {
+    for field in DEPENDENCY_FIELDS {
+        if let Some(deps) = manifest.get(field).and_then(Value::as_object) {
+            names.extend(deps.keys().cloned());
+        }
+    }
+}
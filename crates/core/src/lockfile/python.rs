@@ -0,0 +1,174 @@
+//! Python lockfile parsing: `poetry.lock` and pinned `requirements.txt`
+//!
+//! For Poetry, direct dependencies come from the sibling `pyproject.toml`
+//! (`[tool.poetry.*dependencies]` tables or PEP 621 `[project]` lists). For
+//! `requirements.txt`, only `name==version` pins are components; when the
+//! file carries pip-compile `# via` annotations, a package is direct if it is
+//! required by an input file (`-r ...`), otherwise every pin is direct.
+
+use super::{Ecosystem, ParsedLockfile, ParsedPackage};
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+#[derive(Deserialize)]
+struct PoetryLock {
+    #[serde(default)]
+    package: Vec<PoetryEntry>,
+}
+
+#[derive(Deserialize)]
+struct PoetryEntry {
+    name: String,
+    version: String,
+}
+
+/// Parse a `poetry.lock`; `dir` is the directory containing it
+pub fn parse_poetry(content: &str, dir: &Path) -> Result<ParsedLockfile> {
+    let lock: PoetryLock = toml::from_str(content)?;
+    let pyproject: Option<toml::Value> = std::fs::read_to_string(dir.join("pyproject.toml"))
+        .ok()
+        .and_then(|s| s.parse().ok());
+
+    let direct = pyproject.as_ref().map(direct_names).unwrap_or_default();
+    let project = pyproject.as_ref().and_then(|p| {
+        p.get("project")
+            .and_then(|t| t.get("name"))
+            .or_else(|| p.get("tool")?.get("poetry")?.get("name"))
+            .and_then(|n| n.as_str())
+            .map(String::from)
+    });
+
+    let packages = lock
+        .package
+        .into_iter()
+        .map(|p| ParsedPackage {
+            ecosystem: Ecosystem::PyPI,
+            direct: direct.contains(&Ecosystem::PyPI.normalize_name(&p.name)),
+            name: p.name,
+            version: p.version,
+        })
+        .collect();
+
+    Ok(ParsedLockfile { project, packages })
+}
+
+/// Normalized names of the dependencies declared in `pyproject.toml`
+fn direct_names(pyproject: &toml::Value) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let mut add = |name: &str| {
+        if name != "python" {
+            names.insert(Ecosystem::PyPI.normalize_name(name));
+        }
+    };
+
+    if let Some(poetry) = pyproject.get("tool").and_then(|t| t.get("poetry")) {
+        let mut tables = vec![poetry.get("dependencies"), poetry.get("dev-dependencies")];
+        if let Some(groups) = poetry.get("group").and_then(|g| g.as_table()) {
+            tables.extend(groups.values().map(|g| g.get("dependencies")));
+        }
+        for table in tables.into_iter().flatten().filter_map(|t| t.as_table()) {
+            table.keys().for_each(|k| add(k));
+        }
+    }
+
+    if let Some(project) = pyproject.get("project") {
+        let mut lists = vec![project.get("dependencies")];
+        if let Some(optional) = project
+            .get("optional-dependencies")
+            .and_then(|o| o.as_table())
+        {
+            lists.extend(optional.values().map(Some));
+        }
+        for list in lists.into_iter().flatten().filter_map(|l| l.as_array()) {
+            for spec in list.iter().filter_map(|s| s.as_str()) {
+                add(requirement_name(spec));
+            }
+        }
+    }
+
+    names
+}
+
+/// Parse a `requirements.txt`
+pub fn parse_requirements(content: &str) -> ParsedLockfile {
+    // (name, version, via) — `via` lists what required the pin
+    let mut pins: Vec<(String, String, Vec<String>)> = Vec::new();
+    let mut annotated = false;
+    let mut in_via = false;
+
+    for raw in content.lines() {
+        let line = raw.trim();
+        if let Some(comment) = line.strip_prefix('#') {
+            let comment = comment.trim();
+            if let Some(rest) = comment.strip_prefix("via") {
+                annotated = true;
+                let rest = rest.trim();
+                in_via = rest.is_empty();
+                if let Some(pin) = pins.last_mut().filter(|_| !rest.is_empty()) {
+                    pin.2.push(rest.to_string());
+                }
+            } else if in_via && !comment.is_empty() {
+                if let Some(pin) = pins.last_mut() {
+                    pin.2.push(comment.to_string());
+                }
+            }
+            continue;
+        }
+        in_via = false;
+
+        if line.is_empty() || line.starts_with('-') {
+            continue;
+        }
+        let (spec, comment) = match line.find(" #") {
+            Some(idx) => (&line[..idx], line[idx + 2..].trim()),
+            None => (line, ""),
+        };
+        // Environment markers and hashes follow the pin
+        let spec = spec.split(';').next().unwrap_or(spec);
+        let spec = spec
+            .split(" --")
+            .next()
+            .unwrap_or(spec)
+            .trim_end_matches('\\');
+        let Some((name, version)) = spec.split_once("==") else {
+            continue;
+        };
+        let name = requirement_name(name);
+        let version = version.trim();
+        if name.is_empty() || version.is_empty() {
+            continue;
+        }
+        let mut via = Vec::new();
+        if let Some(rest) = comment.strip_prefix("via") {
+            annotated = true;
+            via.push(rest.trim().to_string());
+        }
+        pins.push((name.to_string(), version.to_string(), via));
+    }
+
+    let packages = pins
+        .into_iter()
+        .map(|(name, version, via)| ParsedPackage {
+            ecosystem: Ecosystem::PyPI,
+            direct: !annotated || via.iter().any(|v| v.starts_with("-r")),
+            name,
+            version,
+        })
+        .collect();
+
+    ParsedLockfile {
+        project: None,
+        packages,
+    }
+}
+
+/// Distribution name from a PEP 508 requirement (`name[extra]>=1; marker`)
+pub(crate) fn requirement_name(spec: &str) -> &str {
+    let spec = spec.trim();
+    let end = spec
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(spec.len());
+    &spec[..end]
+}
@@ -0,0 +1,228 @@
+//! LSP conversion — findings and their fix plans as Language Server Protocol
+//! diagnostics and quick-fix code actions
+//!
+//! Editor integrations (`revet fixes --format lsp-json`, and later a
+//! language server) share this layer so the ranges they report agree with
+//! [`Finding::column`] and the edits agree with what `--fix` writes.
+//!
+//! LSP positions are 0-based lines and UTF-16 code unit offsets; revet's are
+//! 1-based lines and character columns. [`LineIndex`] converts between them.
+
+use crate::analyzer::registry::prefix_of;
+use crate::finding::{Finding, Severity};
+use crate::fixer::{plan_fixes, FixPlan};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A position in a text document (0-based line, UTF-16 offset)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+/// A range in a text document, end exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// A diagnostic as published by `textDocument/publishDiagnostics`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub range: Range,
+    /// 1 = Error, 2 = Warning, 3 = Information
+    pub severity: u8,
+    /// Sub-rule (`error_handling:swallowed`), or the finding prefix for
+    /// analyzers without sub-rules
+    pub code: String,
+    pub source: String,
+    pub message: String,
+}
+
+/// A replacement of `range` with `new_text`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextEdit {
+    pub range: Range,
+    pub new_text: String,
+}
+
+/// Edits per document URI
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceEdit {
+    pub changes: BTreeMap<String, Vec<TextEdit>>,
+}
+
+/// A `quickfix` code action that resolves one diagnostic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeAction {
+    pub title: String,
+    pub kind: String,
+    pub diagnostics: Vec<Diagnostic>,
+    pub edit: WorkspaceEdit,
+    pub is_preferred: bool,
+}
+
+/// One finding: its diagnostic and, when revet can fix it, the fix
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LspFix {
+    pub diagnostic: Diagnostic,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_action: Option<CodeAction>,
+}
+
+/// Line starts of one document, for converting character columns to LSP
+/// positions
+pub struct LineIndex<'a> {
+    lines: Vec<&'a str>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(content: &'a str) -> Self {
+        Self {
+            lines: content.lines().collect(),
+        }
+    }
+
+    /// LSP position of 1-based `line` and 1-based character `column`
+    ///
+    /// Lines past the end clamp to the last line and columns past the end
+    /// of a line clamp to its end.
+    pub fn position(&self, line: usize, column: usize) -> Position {
+        let index = line
+            .saturating_sub(1)
+            .min(self.lines.len().saturating_sub(1));
+        let text = self.lines.get(index).copied().unwrap_or("");
+        let character: usize = text
+            .chars()
+            .take(column.saturating_sub(1))
+            .map(char::len_utf16)
+            .sum();
+        Position {
+            line: index as u32,
+            character: character as u32,
+        }
+    }
+
+    /// LSP position of the end of 1-based `line`
+    pub fn line_end(&self, line: usize) -> Position {
+        self.position(line, usize::MAX)
+    }
+
+    /// Range a finding covers: its columns when known, else the whole line
+    pub fn finding_range(&self, finding: &Finding) -> Range {
+        let start = self.position(finding.line, finding.column.unwrap_or(1));
+        let end = match (finding.column, finding.end_column) {
+            (Some(_), Some(end_column)) => self.position(finding.line, end_column),
+            _ => self.line_end(finding.line),
+        };
+        Range { start, end }
+    }
+
+    /// Range a fix plan replaces; a removed line's range ends at the start
+    /// of the next line
+    pub fn plan_range(&self, plan: &FixPlan) -> Range {
+        if plan.removes_line && plan.line < self.lines.len() {
+            return Range {
+                start: self.position(plan.line, 1),
+                end: Position {
+                    line: plan.line as u32,
+                    character: 0,
+                },
+            };
+        }
+        Range {
+            start: self.position(plan.line, plan.start_column),
+            end: self.position(plan.line, plan.end_column),
+        }
+    }
+}
+
+/// `file://` URI for an absolute path, percent-encoding reserved bytes
+pub fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from("file://");
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+/// LSP diagnostic for `finding`
+pub fn diagnostic(finding: &Finding, index: &LineIndex) -> Diagnostic {
+    let code = finding.rule.clone().unwrap_or_else(|| {
+        let prefix = prefix_of(&finding.id);
+        prefix.to_string()
+    });
+    Diagnostic {
+        range: index.finding_range(finding),
+        severity: match finding.severity {
+            Severity::Error => 1,
+            Severity::Warning => 2,
+            Severity::Info => 3,
+        },
+        code,
+        source: "revet".to_string(),
+        message: finding.message.clone(),
+    }
+}
+
+/// Diagnostics and quick fixes for the `findings` of one document
+///
+/// `path` is the document's absolute path and `content` its current text;
+/// findings for other files are ignored. Fixes are planned with
+/// [`plan_fixes`], so applying every code action's edits gives the same
+/// text as `--fix`. The result is sorted by diagnostic range.
+pub fn fixes_for_document(findings: &[Finding], path: &Path, content: &str) -> Vec<LspFix> {
+    let index = LineIndex::new(content);
+    let uri = file_uri(path);
+    let findings: Vec<&Finding> = findings.iter().filter(|f| f.file == path).collect();
+    let plans = plan_fixes(findings.iter().copied(), content);
+
+    let mut fixes: Vec<LspFix> = findings
+        .iter()
+        .map(|finding| {
+            let diagnostic = diagnostic(finding, &index);
+            let code_action = plans
+                .iter()
+                .find(|plan| plan.finding_id == finding.id)
+                .map(|plan| CodeAction {
+                    title: format!(
+                        "Fix with revet: {}",
+                        finding.suggestion.as_deref().unwrap_or(&finding.message)
+                    ),
+                    kind: "quickfix".to_string(),
+                    diagnostics: vec![diagnostic.clone()],
+                    edit: WorkspaceEdit {
+                        changes: BTreeMap::from([(
+                            uri.clone(),
+                            vec![TextEdit {
+                                range: index.plan_range(plan),
+                                new_text: plan.new_text.clone(),
+                            }],
+                        )]),
+                    },
+                    is_preferred: true,
+                });
+            LspFix {
+                diagnostic,
+                code_action,
+            }
+        })
+        .collect();
+    fixes.sort_by_key(|fix| fix.diagnostic.range);
+    fixes
+}
@@ -0,0 +1,352 @@
+//! Memory ceiling of a review — `[performance] max_memory_mb`
+//!
+//! A CI container's memory limit is enforced by the kernel, which kills the
+//! process without a line of output. With a ceiling, a [`MemoryGuard`]
+//! samples the resident set size of the process in the background, and the
+//! review checks it at each stage boundary. Past the high-water mark
+//! ([`HIGH_WATER_PERCENT`] of the ceiling) the review sheds load, one
+//! [`Shedding`] step per boundary, in this order:
+//!
+//! 1. smaller analyzer batches: per-file content analyzers run one
+//!    directory partition at a time
+//! 2. in-memory caches dropped: the base-tree file contents complexity
+//!    delta mode and refactor verification keep
+//! 3. graph analyzers on the graph store instead of the in-memory graph,
+//!    where that is available
+//! 4. the most memory-hungry remaining analyzers skipped, ranked by their
+//!    recorded peak allocation
+//!
+//! A step with nothing to act on at that boundary is recorded as
+//! unavailable and the next one is taken at once. Step 4 repeats while
+//! usage stays between the high-water mark and the ceiling. Once every step
+//! was taken and usage is still over the ceiling, the review stops where it
+//! is: the findings so far are reported, and the run exits with the
+//! environment-error code instead of being killed.
+//!
+//! Resident memory is read from `/proc/self/status` on Linux; elsewhere it
+//! is unknown and the ceiling is not enforced.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::config::PerformanceConfig;
+
+/// Share of the ceiling past which the review sheds load, in percent
+pub const HIGH_WATER_PERCENT: u64 = 85;
+
+/// How often the background thread reads the resident set size
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(50);
+
+const MB: u64 = 1024 * 1024;
+
+/// Resident set size of this process in bytes; `None` where it can't be
+/// read
+pub fn resident_bytes() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+        let kb: u64 = line
+            .trim_start_matches("VmRSS:")
+            .trim()
+            .trim_end_matches("kB")
+            .trim()
+            .parse()
+            .ok()?;
+        Some(kb * 1024)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Ways to shed load, in the order they are taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Shedding {
+    SmallerBatches,
+    DropCaches,
+    LazyGraph,
+    SkipAnalyzers,
+}
+
+impl Shedding {
+    /// Every step, in order
+    pub const ALL: [Shedding; 4] = [
+        Shedding::SmallerBatches,
+        Shedding::DropCaches,
+        Shedding::LazyGraph,
+        Shedding::SkipAnalyzers,
+    ];
+
+    /// How the step is named in the summary
+    pub fn label(self) -> &'static str {
+        match self {
+            Shedding::SmallerBatches => "smaller analyzer batches",
+            Shedding::DropCaches => "in-memory caches dropped",
+            Shedding::LazyGraph => "store-backed graph analyzers",
+            Shedding::SkipAnalyzers => "memory-hungry analyzers skipped",
+        }
+    }
+}
+
+/// One shedding step a review took
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ShedStep {
+    pub step: Shedding,
+    /// The stage the review was about to start
+    pub stage: String,
+    /// Resident memory when the step was taken
+    pub rss_mb: u64,
+    /// Whether the step had anything to act on
+    pub applied: bool,
+    /// What the step did, or why it could not
+    pub detail: String,
+}
+
+impl fmt::Display for ShedStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} before {} at {} MB: {}",
+            self.step.label(),
+            self.stage,
+            self.rss_mb,
+            self.detail
+        )?;
+        if !self.applied {
+            write!(f, " (unavailable)")?;
+        }
+        Ok(())
+    }
+}
+
+/// How a review fared against its memory ceiling
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MemoryReport {
+    pub max_memory_mb: u64,
+    /// Highest resident memory sampled; 0 where it can't be read
+    pub peak_mb: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shed: Vec<ShedStep>,
+    /// Whether the review stopped over the ceiling: the findings then
+    /// cover only the stages before `stopped_before`
+    pub exceeded: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stopped_before: Option<String>,
+}
+
+type Probe = Arc<dyn Fn() -> Option<u64> + Send + Sync>;
+
+/// Background thread keeping the highest reading of a probe
+struct Sampler {
+    peak: Arc<AtomicU64>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Sampler {
+    fn start(probe: Probe) -> Self {
+        let peak = Arc::new(AtomicU64::new(0));
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let (peak, stop) = (peak.clone(), stop.clone());
+            thread::Builder::new()
+                .name("revet-memory".to_string())
+                .spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        if let Some(bytes) = probe() {
+                            peak.fetch_max(bytes, Ordering::Relaxed);
+                        }
+                        thread::park_timeout(SAMPLE_INTERVAL);
+                    }
+                })
+                .ok()
+        };
+        Self { peak, stop, handle }
+    }
+
+    /// The highest reading since the last call
+    fn take_peak(&self) -> u64 {
+        self.peak.swap(0, Ordering::Relaxed)
+    }
+}
+
+impl Drop for Sampler {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Watches a review's resident memory against `[performance]
+/// max_memory_mb` and decides when to shed load or stop
+pub struct MemoryGuard {
+    ceiling: u64,
+    probe: Probe,
+    sampler: Option<Sampler>,
+    peak: u64,
+    shed: Vec<ShedStep>,
+    stopped_before: Option<String>,
+}
+
+impl fmt::Debug for MemoryGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryGuard")
+            .field("ceiling", &self.ceiling)
+            .field("peak", &self.peak)
+            .field("shed", &self.shed)
+            .field("stopped_before", &self.stopped_before)
+            .finish()
+    }
+}
+
+impl MemoryGuard {
+    /// A guard sampling this process's resident memory against a ceiling
+    /// of `max_memory_mb`
+    pub fn new(max_memory_mb: u64) -> Self {
+        Self::with_probe(max_memory_mb, resident_bytes)
+    }
+
+    /// A guard reading memory use in bytes from `probe` instead of the
+    /// process
+    pub fn with_probe(
+        max_memory_mb: u64,
+        probe: impl Fn() -> Option<u64> + Send + Sync + 'static,
+    ) -> Self {
+        let probe: Probe = Arc::new(probe);
+        let sampler = probe().map(|_| Sampler::start(probe.clone()));
+        Self {
+            ceiling: max_memory_mb.saturating_mul(MB),
+            probe,
+            sampler,
+            peak: 0,
+            shed: Vec::new(),
+            stopped_before: None,
+        }
+    }
+
+    /// The guard `[performance] max_memory_mb` asks for, if any
+    pub fn from_config(config: &PerformanceConfig) -> Option<Self> {
+        config.max_memory_mb.map(Self::new)
+    }
+
+    /// Whether memory use can be read here; without it the ceiling is not
+    /// enforced
+    pub fn is_enforced(&self) -> bool {
+        self.sampler.is_some()
+    }
+
+    /// Usage past which load is shed, in bytes
+    pub fn high_water(&self) -> u64 {
+        self.ceiling / 100 * HIGH_WATER_PERCENT
+    }
+
+    /// The higher of the current reading and the peak sampled since the
+    /// last checkpoint
+    fn usage(&mut self) -> Option<u64> {
+        let sampler = self.sampler.as_ref()?;
+        let usage = (self.probe)().unwrap_or(0).max(sampler.take_peak());
+        self.peak = self.peak.max(usage);
+        Some(usage)
+    }
+
+    fn taken(&self, step: Shedding) -> bool {
+        self.shed.iter().any(|s| s.step == step)
+    }
+
+    /// Shed load before `stage` when usage is past the high-water mark.
+    /// `shed` takes one step and returns what it did, or `None` when the
+    /// step has nothing to act on at this point; the next step is then
+    /// tried. Returns whether the review must stop: every step was taken
+    /// and usage is still over the ceiling.
+    pub fn checkpoint(
+        &mut self,
+        stage: &str,
+        mut shed: impl FnMut(Shedding) -> Option<String>,
+    ) -> bool {
+        if self.stopped_before.is_some() {
+            return true;
+        }
+        let Some(usage) = self.usage() else {
+            return false;
+        };
+        if usage < self.high_water() {
+            return false;
+        }
+        let over = usage >= self.ceiling;
+        let mut pending: Vec<Shedding> = Shedding::ALL
+            .into_iter()
+            .filter(|step| !self.taken(*step))
+            .collect();
+        if pending.is_empty() {
+            if over {
+                self.stopped_before = Some(stage.to_string());
+                return true;
+            }
+            pending.push(Shedding::SkipAnalyzers);
+        }
+        for step in pending {
+            let (applied, detail) = match shed(step) {
+                Some(detail) => (true, detail),
+                None if self.taken(step) => continue,
+                None => (false, unavailable(step).to_string()),
+            };
+            self.shed.push(ShedStep {
+                step,
+                stage: stage.to_string(),
+                rss_mb: usage / MB,
+                applied,
+                detail,
+            });
+            if applied {
+                return false;
+            }
+        }
+        if over {
+            self.stopped_before = Some(stage.to_string());
+        }
+        over
+    }
+
+    /// The steps taken so far, in order
+    pub fn shed(&self) -> &[ShedStep] {
+        &self.shed
+    }
+
+    /// Whether the review stopped over the ceiling
+    pub fn is_stopped(&self) -> bool {
+        self.stopped_before.is_some()
+    }
+
+    pub fn report(&self) -> MemoryReport {
+        MemoryReport {
+            max_memory_mb: self.ceiling / MB,
+            peak_mb: self.peak / MB,
+            shed: self.shed.clone(),
+            exceeded: self.stopped_before.is_some(),
+            stopped_before: self.stopped_before.clone(),
+        }
+    }
+}
+
+/// Why `step` had nothing to act on
+fn unavailable(step: Shedding) -> &'static str {
+    match step {
+        Shedding::SmallerBatches => "no analyzer batches left to run",
+        Shedding::DropCaches => "nothing cached in memory",
+        Shedding::LazyGraph => "graph analyzers only run on the in-memory graph",
+        Shedding::SkipAnalyzers => "no analyzers left to skip",
+    }
+}
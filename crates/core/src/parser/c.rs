@@ -84,6 +84,7 @@ impl CParser {
             0,
             NodeData::File {
                 language: lang_name.to_string(),
+                code_kind: crate::code_kind::classify(file_path, graph.root_path(), source),
             },
         );
         let file_node_id = graph.add_node(file_node);
@@ -653,12 +654,13 @@ impl LanguageParser for CParser {
         Ok(self.extract_nodes(&tree, source, file_path, graph, is_cpp))
     }
 
-    fn parse_file_with_state(
+    fn parse_source_with_state(
         &self,
+        source: &str,
         file_path: &Path,
         graph: &mut CodeGraph,
     ) -> Result<(Vec<NodeId>, ParseState), ParseError> {
-        let ids = self.parse_file(file_path, graph)?;
+        let ids = self.parse_source(source, file_path, graph)?;
         let state = collect_import_state(graph, file_path);
         Ok((ids, state))
     }
@@ -0,0 +1,1406 @@
+//! Parser conformance fixtures
+//!
+//! What a [`LanguageParser`](super::LanguageParser) should extract, as data
+//! rather than tribal knowledge. [`generate`] writes a deterministic
+//! synthetic source file for a language and seed — classes nested several
+//! levels deep, a function with every parameter style of the language,
+//! imports of each form, decorators, a Unicode identifier, an enormous
+//! string literal and deeply nested blocks — together with the [`Manifest`]
+//! of entities a parser should extract from it. [`check`] compares a parsed
+//! graph against the manifest, one [`Check`] at a time.
+//!
+//! The manifest's conventions:
+//!
+//! - names are qualified with `.` by the enclosing classes, modules and
+//!   namespace blocks, but not by the file's package or namespace
+//!   declaration; Go and Rust methods by their receiver type
+//! - an entity's line is the line of its name, below any decorators, and
+//!   its end line the last line of its body
+//! - decorators are compared by name: `@cached`, `#[inline]`,
+//!   `[Obsolete]` and `@Deprecated("x")` are `cached`, `inline`,
+//!   `Obsolete` and `Deprecated`
+//! - parameters are named without sigils (`args` for `*args`, `rest` for
+//!   `...rest`), receivers (`self`, `this`) excluded
+//! - an import is identified by the module it names, and is reached from
+//!   its file by an [`EdgeKind::Imports`] edge rather than contained
+//!
+//! The same language and seed always produce the same file.
+
+use crate::graph::{CodeGraph, EdgeKind, Node, NodeData, NodeId, NodeKind};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Deterministic pseudo-random numbers (SplitMix64)
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `low..=high`
+    pub fn range(&mut self, low: usize, high: usize) -> usize {
+        low + (self.next_u64() % (high - low + 1) as u64) as usize
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0, i));
+        }
+    }
+}
+
+/// A generated source file and what a parser should extract from it
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    /// File name with the language's extension: `conformance_42.swift`
+    pub file_name: String,
+    pub source: String,
+    pub manifest: Manifest,
+}
+
+/// The entities a parser should extract from a [`Fixture`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub language: String,
+    pub seed: u64,
+    pub entities: Vec<Expected>,
+    /// Number of entities of each kind
+    pub counts: BTreeMap<String, usize>,
+}
+
+/// One entity of a [`Manifest`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Expected {
+    pub kind: NodeKind,
+    /// Qualified name; for imports, the module
+    pub name: String,
+    pub line: usize,
+    pub end_line: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub decorators: Vec<String>,
+    /// Parameter names, for the functions whose parameters are checked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Vec<String>>,
+    /// Qualified name of the enclosing entity, `None` at file level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+}
+
+/// An aspect of what a parser extracts, checked entity by entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Check {
+    /// The entity is not extracted
+    Missing,
+    /// The entity is extracted as another kind of node
+    Kind,
+    /// The entity's name is not qualified by its enclosing entities
+    QualifiedName,
+    Line,
+    EndLine,
+    Decorators,
+    Parameters,
+    /// The entity has no [`EdgeKind::Contains`] edge from its enclosing
+    /// entity (or file)
+    Containment,
+    /// More or fewer nodes of a kind than the manifest lists
+    Count,
+    /// [`CodeGraph::validate`] fails
+    Invariant,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Check::Missing => "missing",
+            Check::Kind => "kind",
+            Check::QualifiedName => "qualified_name",
+            Check::Line => "line",
+            Check::EndLine => "end_line",
+            Check::Decorators => "decorators",
+            Check::Parameters => "parameters",
+            Check::Containment => "containment",
+            Check::Count => "count",
+            Check::Invariant => "invariant",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A difference between a parsed graph and a [`Manifest`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub check: Check,
+    /// The entity's name, or the node kind for [`Check::Count`]
+    pub entity: String,
+    pub detail: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} `{}`: {}", self.check, self.entity, self.detail)
+    }
+}
+
+/// Language names [`generate`] has fixtures for, as the parsers'
+/// [`language_name`](super::LanguageParser::language_name)s
+pub fn languages() -> impl Iterator<Item = &'static str> {
+    LANGUAGES.iter().map(|l| l.name)
+}
+
+/// The fixture for `language` — a parser's language name or a file
+/// extension (`swift`, `py`, `c`) — and `seed`
+pub fn generate(language: &str, seed: u64) -> Option<Fixture> {
+    let language = LANGUAGES
+        .iter()
+        .find(|l| l.name == language || l.extension == language)?;
+    let mut rng = Rng::new(seed);
+    let plan = Plan::new(&mut rng);
+    let mut source = Source::default();
+    (language.write)(&mut source, &plan);
+
+    let mut counts = BTreeMap::new();
+    for entity in &source.entities {
+        *counts.entry(kind_name(entity.kind)).or_insert(0) += 1;
+    }
+    Some(Fixture {
+        file_name: format!("conformance_{}.{}", seed, language.extension),
+        source: source.lines.join("\n") + "\n",
+        manifest: Manifest {
+            language: language.name.to_string(),
+            seed,
+            entities: source.entities,
+            counts,
+        },
+    })
+}
+
+/// Differences between `graph`, parsed from the fixture alone, and the
+/// fixture's manifest
+pub fn check(manifest: &Manifest, graph: &CodeGraph) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    let mut report = |check: Check, entity: &str, detail: String| {
+        mismatches.push(Mismatch {
+            check,
+            entity: entity.to_string(),
+            detail,
+        });
+    };
+
+    if let Err(violations) = graph.validate() {
+        for violation in violations {
+            report(Check::Invariant, "graph", violation.to_string());
+        }
+    }
+
+    let file = graph
+        .nodes()
+        .find(|(_, n)| *n.kind() == NodeKind::File)
+        .map(|(id, _)| id);
+
+    for expected in &manifest.entities {
+        let Some((id, node, qualified)) = find(graph, expected) else {
+            report(Check::Missing, &expected.name, "not extracted".into());
+            continue;
+        };
+        if !qualified {
+            report(
+                Check::QualifiedName,
+                &expected.name,
+                format!("named `{}`", node.name()),
+            );
+        }
+        if *node.kind() != expected.kind {
+            report(
+                Check::Kind,
+                &expected.name,
+                format!("{:?}, expected {:?}", node.kind(), expected.kind),
+            );
+        }
+        if node.line() != expected.line {
+            report(
+                Check::Line,
+                &expected.name,
+                format!("line {}, expected {}", node.line(), expected.line),
+            );
+        }
+        if expected.kind == NodeKind::Import {
+            continue;
+        }
+        if node.end_line() != Some(expected.end_line) {
+            report(
+                Check::EndLine,
+                &expected.name,
+                format!(
+                    "end line {:?}, expected {}",
+                    node.end_line(),
+                    expected.end_line
+                ),
+            );
+        }
+        let decorators: Vec<String> = node
+            .decorators()
+            .iter()
+            .map(|d| decorator_name(d))
+            .collect();
+        if decorators != expected.decorators {
+            report(
+                Check::Decorators,
+                &expected.name,
+                format!(
+                    "{:?}, expected {:?}",
+                    node.decorators(),
+                    expected.decorators
+                ),
+            );
+        }
+        if let (
+            Some(parameters),
+            NodeData::Function {
+                parameters: found, ..
+            },
+        ) = (&expected.parameters, node.data())
+        {
+            let found: Vec<&str> = found.iter().map(|p| p.name.as_str()).collect();
+            if found != *parameters {
+                report(
+                    Check::Parameters,
+                    &expected.name,
+                    format!("{:?}, expected {:?}", found, parameters),
+                );
+            }
+        }
+        let parent = match &expected.parent {
+            None => file,
+            Some(parent) => manifest
+                .entities
+                .iter()
+                .find(|e| e.name == *parent)
+                .and_then(|e| find(graph, e))
+                .map(|(id, _, _)| id),
+        };
+        let contained = parent.is_some_and(|p| {
+            graph
+                .edges_from(p)
+                .any(|(to, e)| to == id && *e.kind() == EdgeKind::Contains)
+        });
+        if !contained {
+            report(
+                Check::Containment,
+                &expected.name,
+                format!(
+                    "not contained in {}",
+                    expected.parent.as_deref().unwrap_or("its file")
+                ),
+            );
+        }
+    }
+
+    for (kind, count) in &manifest.counts {
+        let found = graph
+            .nodes()
+            .filter(|(_, n)| kind_name(*n.kind()) == *kind)
+            .count();
+        if found != *count {
+            report(
+                Check::Count,
+                kind,
+                format!("{} nodes, expected {}", found, count),
+            );
+        }
+    }
+
+    mismatches
+}
+
+/// The node extracted for `expected`, and whether its name is the qualified
+/// one; a node named by the last segment, a partly qualified name or a name
+/// qualified further (by a package) only when no node has the qualified
+/// name, and near the expected line
+fn find<'g>(graph: &'g CodeGraph, expected: &Expected) -> Option<(NodeId, &'g Node, bool)> {
+    if expected.kind == NodeKind::Import {
+        return graph
+            .nodes()
+            .find(|(_, n)| {
+                matches!(n.data(), NodeData::Import { module, .. } if *module == expected.name)
+            })
+            .map(|(id, n)| (id, n, true));
+    }
+    let declared = |n: &Node| !matches!(n.kind(), NodeKind::File | NodeKind::Import);
+    if let Some((id, node)) = graph
+        .nodes()
+        .find(|(_, n)| declared(n) && n.name() == expected.name)
+    {
+        return Some((id, node, true));
+    }
+    let last = expected.name.rsplit('.').next().unwrap_or(&expected.name);
+    graph
+        .nodes()
+        .filter(|(_, n)| declared(n) && n.line() <= expected.line + 1)
+        .find(|(_, n)| {
+            let name = n.name();
+            (name == last
+                || expected.name.ends_with(&format!(".{}", name))
+                || name.ends_with(&format!(".{}", expected.name)))
+                && n.line() + 1 >= expected.line
+        })
+        .map(|(id, n)| (id, n, false))
+}
+
+/// `cached` for `@cached`, `#[inline]`, `[Obsolete]` or `@Deprecated("x")`
+fn decorator_name(decorator: &str) -> String {
+    let name = decorator
+        .trim()
+        .trim_start_matches('@')
+        .trim_start_matches("#[")
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    name.split('(').next().unwrap_or(name).trim().to_string()
+}
+
+fn kind_name(kind: NodeKind) -> String {
+    format!("{:?}", kind)
+}
+
+const TYPE_NAMES: &[&str] = &["Outer", "Middle", "Inner", "Nested", "Leaf", "Core"];
+const METHOD_NAMES: &[&str] = &["run", "load", "save", "reset", "apply", "render"];
+const PARAM_NAMES: &[&str] = &["alpha", "beta", "gamma", "delta", "epsilon", "zeta", "eta"];
+const UNICODE_NAMES: &[&str] = &["größe", "données", "naïve_ñ", "数据", "πάνω"];
+const DECORATORS: &[&str] = &["Traced", "Cached", "Audited"];
+
+/// What one seed varies: names, nesting depth, sizes
+struct Plan {
+    /// Nested types, outermost first
+    types: Vec<&'static str>,
+    /// One method per nested type
+    methods: Vec<&'static str>,
+    params: Vec<&'static str>,
+    unicode: &'static str,
+    decorator: &'static str,
+    /// Length of the string literal
+    literal: String,
+    /// Nesting depth of the blocks in `deep`
+    blocks: usize,
+}
+
+impl Plan {
+    fn new(rng: &mut Rng) -> Self {
+        let mut types = TYPE_NAMES.to_vec();
+        rng.shuffle(&mut types);
+        types.truncate(rng.range(2, 5));
+        let mut methods = METHOD_NAMES.to_vec();
+        rng.shuffle(&mut methods);
+        methods.truncate(types.len());
+        let mut params = PARAM_NAMES.to_vec();
+        rng.shuffle(&mut params);
+        let unicode = *rng.pick(UNICODE_NAMES);
+        let decorator = *rng.pick(DECORATORS);
+        let length = rng.range(4_096, 65_536);
+        let literal = (0..length)
+            .map(|_| *rng.pick(b"abcdefghijklmnopqrstuvwxyz 0123456789") as char)
+            .collect();
+        let blocks = rng.range(16, 48);
+        Self {
+            types,
+            methods,
+            params,
+            unicode,
+            decorator,
+            literal,
+            blocks,
+        }
+    }
+
+    /// Parameter names, the `n`th being `p[n]`
+    fn p(&self, n: usize) -> &'static str {
+        self.params[n]
+    }
+}
+
+/// Source lines and the entities declared in them
+#[derive(Default)]
+struct Source {
+    lines: Vec<String>,
+    entities: Vec<Expected>,
+}
+
+impl Source {
+    /// Append a line; returns its number
+    fn line(&mut self, indent: usize, text: impl AsRef<str>) -> usize {
+        self.lines
+            .push(format!("{}{}", "    ".repeat(indent), text.as_ref()));
+        self.lines.len()
+    }
+
+    fn blank(&mut self) {
+        self.lines.push(String::new());
+    }
+
+    /// Append the line declaring an entity; returns the entity's index
+    fn open(
+        &mut self,
+        indent: usize,
+        text: impl AsRef<str>,
+        kind: NodeKind,
+        name: String,
+        parent: Option<&str>,
+    ) -> usize {
+        let line = self.line(indent, text);
+        self.entities.push(Expected {
+            kind,
+            name,
+            line,
+            end_line: line,
+            decorators: Vec::new(),
+            parameters: None,
+            parent: parent.map(str::to_string),
+        });
+        self.entities.len() - 1
+    }
+
+    /// Append an entity's last line
+    fn close(&mut self, entity: usize, indent: usize, text: impl AsRef<str>) {
+        let line = self.line(indent, text);
+        self.entities[entity].end_line = line;
+    }
+
+    /// Mark the previous line as an entity's last
+    fn ends(&mut self, entity: usize) {
+        self.entities[entity].end_line = self.lines.len();
+    }
+
+    fn import(&mut self, text: impl AsRef<str>, module: &str) {
+        self.open(0, text, NodeKind::Import, module.to_string(), None);
+    }
+
+    fn decorate(&mut self, entity: usize, decorator: &str) {
+        self.entities[entity].decorators.push(decorator.to_string());
+    }
+
+    fn parameters(&mut self, entity: usize, names: &[&str]) {
+        self.entities[entity].parameters = Some(names.iter().map(|n| n.to_string()).collect());
+    }
+
+    /// `depth` blocks opened by `open` and closed by `close`, nested
+    fn blocks(&mut self, indent: usize, depth: usize, open: &str, close: &str) {
+        for level in 0..depth {
+            self.line(indent + level, open);
+        }
+        for level in (0..depth).rev() {
+            self.line(indent + level, close);
+        }
+    }
+}
+
+fn qualify(parent: Option<&str>, name: &str) -> String {
+    match parent {
+        Some(parent) => format!("{}.{}", parent, name),
+        None => name.to_string(),
+    }
+}
+
+struct Language {
+    name: &'static str,
+    extension: &'static str,
+    write: fn(&mut Source, &Plan),
+}
+
+const LANGUAGES: &[Language] = &[
+    Language {
+        name: "c/cpp",
+        extension: "c",
+        write: write_c,
+    },
+    Language {
+        name: "csharp",
+        extension: "cs",
+        write: write_csharp,
+    },
+    Language {
+        name: "go",
+        extension: "go",
+        write: write_go,
+    },
+    Language {
+        name: "java",
+        extension: "java",
+        write: write_java,
+    },
+    Language {
+        name: "kotlin",
+        extension: "kt",
+        write: write_kotlin,
+    },
+    Language {
+        name: "php",
+        extension: "php",
+        write: write_php,
+    },
+    Language {
+        name: "python",
+        extension: "py",
+        write: write_python,
+    },
+    Language {
+        name: "ruby",
+        extension: "rb",
+        write: write_ruby,
+    },
+    Language {
+        name: "rust",
+        extension: "rs",
+        write: write_rust,
+    },
+    Language {
+        name: "swift",
+        extension: "swift",
+        write: write_swift,
+    },
+    Language {
+        name: "typescript",
+        extension: "ts",
+        write: write_typescript,
+    },
+];
+
+/// Nested types with braces, a method in each after its nested type, the
+/// innermost method and the outermost type decorated by `decorate`
+fn nested_braced(
+    src: &mut Source,
+    plan: &Plan,
+    indent: usize,
+    parent: Option<&str>,
+    header: &dyn Fn(usize, &str) -> String,
+    method: &dyn Fn(&str) -> String,
+    decorate: Option<&dyn Fn(&str) -> String>,
+) {
+    #[allow(clippy::too_many_arguments)]
+    fn level(
+        src: &mut Source,
+        plan: &Plan,
+        depth: usize,
+        indent: usize,
+        parent: Option<&str>,
+        header: &dyn Fn(usize, &str) -> String,
+        method: &dyn Fn(&str) -> String,
+        decorate: Option<&dyn Fn(&str) -> String>,
+    ) {
+        let name = qualify(parent, plan.types[depth]);
+        let innermost = depth + 1 == plan.types.len();
+        if let (Some(decorate), 0) = (decorate, depth) {
+            src.line(indent, decorate(plan.decorator));
+        }
+        let ty = src.open(
+            indent,
+            header(depth, plan.types[depth]),
+            NodeKind::Class,
+            name.clone(),
+            parent,
+        );
+        if depth == 0 && decorate.is_some() {
+            src.decorate(ty, plan.decorator);
+        }
+        if !innermost {
+            level(
+                src,
+                plan,
+                depth + 1,
+                indent + 1,
+                Some(&name),
+                header,
+                method,
+                decorate,
+            );
+        }
+        if let (Some(decorate), true) = (decorate, innermost) {
+            src.line(indent + 1, decorate(plan.decorator));
+        }
+        let m = src.open(
+            indent + 1,
+            method(plan.methods[depth]),
+            NodeKind::Function,
+            qualify(Some(&name), plan.methods[depth]),
+            Some(&name),
+        );
+        if innermost && decorate.is_some() {
+            src.decorate(m, plan.decorator);
+        }
+        src.close(m, indent + 1, "}");
+        src.close(ty, indent, "}");
+    }
+    level(src, plan, 0, indent, parent, header, method, decorate);
+}
+
+/// A function of `name` in braces holding `body`
+fn braced_function(
+    src: &mut Source,
+    indent: usize,
+    header: String,
+    name: &str,
+    parent: Option<&str>,
+    body: &[String],
+) -> usize {
+    let f = src.open(
+        indent,
+        header,
+        NodeKind::Function,
+        qualify(parent, name),
+        parent,
+    );
+    for line in body {
+        src.line(indent + 1, line);
+    }
+    src.close(f, indent, "}");
+    f
+}
+
+/// `blob`, `deep` and the Unicode-named function, brace style
+#[allow(clippy::too_many_arguments)]
+fn braced_edge_cases(
+    src: &mut Source,
+    plan: &Plan,
+    indent: usize,
+    parent: Option<&str>,
+    header: &dyn Fn(&str) -> String,
+    literal: &dyn Fn(&str) -> String,
+    condition: &str,
+    statement: &str,
+) {
+    src.blank();
+    braced_function(
+        src,
+        indent,
+        header(plan.unicode),
+        plan.unicode,
+        parent,
+        &[statement.to_string()],
+    );
+    src.blank();
+    braced_function(
+        src,
+        indent,
+        header("blob"),
+        "blob",
+        parent,
+        &[literal(&plan.literal)],
+    );
+    src.blank();
+    let f = src.open(
+        indent,
+        header("deep"),
+        NodeKind::Function,
+        qualify(parent, "deep"),
+        parent,
+    );
+    src.blocks(indent + 1, plan.blocks, &format!("{} {{", condition), "}");
+    src.close(f, indent, "}");
+}
+
+fn write_c(src: &mut Source, plan: &Plan) {
+    src.import("#include <stdio.h>", "stdio.h");
+    src.import("#include \"local/config.h\"", "local/config.h");
+    src.blank();
+    // Nested struct tags are file scope in C: not qualified
+    let mut open = Vec::new();
+    for (depth, ty) in plan.types.iter().enumerate() {
+        let parent = depth.checked_sub(1).map(|d| plan.types[d]);
+        open.push(src.open(
+            depth,
+            format!("struct {} {{", ty),
+            NodeKind::Class,
+            ty.to_string(),
+            parent,
+        ));
+        src.line(depth + 1, format!("int {};", plan.methods[depth]));
+    }
+    for depth in (0..plan.types.len()).rev() {
+        let field = plan.types[depth].to_lowercase();
+        let close = if depth == 0 {
+            "};".to_string()
+        } else {
+            format!("}} {};", field)
+        };
+        src.close(open[depth], depth, close);
+    }
+    src.blank();
+    let f = braced_function(
+        src,
+        0,
+        format!(
+            "int params(int {}, const char *{}, int {}[], ...) {{",
+            plan.p(0),
+            plan.p(1),
+            plan.p(2)
+        ),
+        "params",
+        None,
+        &["return 0;".to_string()],
+    );
+    src.parameters(f, &[plan.p(0), plan.p(1), plan.p(2)]);
+    braced_edge_cases(
+        src,
+        plan,
+        0,
+        None,
+        &|name| format!("void {}(void) {{", name),
+        &|text| format!("const char *text = \"{}\";", text),
+        "if (1)",
+        "return;",
+    );
+}
+
+fn write_csharp(src: &mut Source, plan: &Plan) {
+    src.import("using System;", "System");
+    src.import(
+        "using System.Collections.Generic;",
+        "System.Collections.Generic",
+    );
+    src.import("using Text = System.Text;", "System.Text");
+    src.import("using static System.Math;", "System.Math");
+    src.blank();
+    src.line(0, "namespace Conformance;");
+    src.blank();
+    nested_braced(
+        src,
+        plan,
+        0,
+        None,
+        &|_, name| format!("public class {} {{", name),
+        &|name| format!("public void {}() {{", name),
+        Some(&|name| format!("[{}]", name)),
+    );
+    src.blank();
+    let holder = src.open(
+        0,
+        "public static class Functions {",
+        NodeKind::Class,
+        "Functions".into(),
+        None,
+    );
+    let f = braced_function(
+        src,
+        1,
+        format!(
+            "public static void Params(int {}, string {} = \"x\", ref int {}, out int {}, params int[] {}) {{",
+            plan.p(0),
+            plan.p(1),
+            plan.p(2),
+            plan.p(3),
+            plan.p(4)
+        ),
+        "Params",
+        Some("Functions"),
+        &[format!("{} = 0;", plan.p(3))],
+    );
+    src.parameters(f, &[plan.p(0), plan.p(1), plan.p(2), plan.p(3), plan.p(4)]);
+    braced_edge_cases(
+        src,
+        plan,
+        1,
+        Some("Functions"),
+        &|name| format!("public static void {}() {{", name),
+        &|text| format!("var text = \"{}\";", text),
+        "if (true)",
+        "return;",
+    );
+    src.close(holder, 0, "}");
+}
+
+fn write_go(src: &mut Source, plan: &Plan) {
+    src.line(0, "package conformance");
+    src.blank();
+    src.import("import \"fmt\"", "fmt");
+    src.line(0, "import (");
+    src.open(1, "alias \"os\"", NodeKind::Import, "os".into(), None);
+    src.open(1, ". \"strings\"", NodeKind::Import, "strings".into(), None);
+    src.open(
+        1,
+        "_ \"net/http\"",
+        NodeKind::Import,
+        "net/http".into(),
+        None,
+    );
+    src.line(0, ")");
+    // Go has no nested types: one struct per level, each with a method
+    for (ty, method) in plan.types.iter().zip(&plan.methods) {
+        src.blank();
+        let s = src.open(
+            0,
+            format!("type {} struct {{", ty),
+            NodeKind::Class,
+            ty.to_string(),
+            None,
+        );
+        src.line(1, "value int");
+        src.close(s, 0, "}");
+        src.blank();
+        braced_function(
+            src,
+            0,
+            format!("func (r *{}) {}() {{", ty, method),
+            &format!("{}.{}", ty, method),
+            None,
+            &["fmt.Println(r.value)".to_string()],
+        );
+    }
+    src.blank();
+    let f = braced_function(
+        src,
+        0,
+        format!(
+            "func params({}, {} int, {} string, {} ...string) {{",
+            plan.p(0),
+            plan.p(1),
+            plan.p(2),
+            plan.p(3)
+        ),
+        "params",
+        None,
+        &[],
+    );
+    src.parameters(f, &[plan.p(0), plan.p(1), plan.p(2), plan.p(3)]);
+    braced_edge_cases(
+        src,
+        plan,
+        0,
+        None,
+        &|name| format!("func {}() {{", name),
+        &|text| format!("text := \"{}\"", text),
+        "if true",
+        "return",
+    );
+}
+
+fn write_java(src: &mut Source, plan: &Plan) {
+    src.line(0, "package conformance;");
+    src.blank();
+    src.import("import java.util.List;", "java.util.List");
+    src.import("import java.util.*;", "java.util");
+    src.import("import static java.lang.Math.max;", "java.lang.Math.max");
+    src.blank();
+    nested_braced(
+        src,
+        plan,
+        0,
+        None,
+        &|depth, name| {
+            let modifier = if depth == 0 {
+                "public"
+            } else {
+                "public static"
+            };
+            format!("{} class {} {{", modifier, name)
+        },
+        &|name| format!("public void {}() {{", name),
+        Some(&|name| format!("@{}", name)),
+    );
+    src.blank();
+    let holder = src.open(
+        0,
+        "final class Functions {",
+        NodeKind::Class,
+        "Functions".into(),
+        None,
+    );
+    let f = braced_function(
+        src,
+        1,
+        format!(
+            "static void params(int {}, final String {}, int... {}) {{",
+            plan.p(0),
+            plan.p(1),
+            plan.p(2)
+        ),
+        "params",
+        Some("Functions"),
+        &[],
+    );
+    src.parameters(f, &[plan.p(0), plan.p(1), plan.p(2)]);
+    braced_edge_cases(
+        src,
+        plan,
+        1,
+        Some("Functions"),
+        &|name| format!("static void {}() {{", name),
+        &|text| format!("String text = \"{}\";", text),
+        "if (true)",
+        "return;",
+    );
+    src.close(holder, 0, "}");
+}
+
+fn write_kotlin(src: &mut Source, plan: &Plan) {
+    src.line(0, "package conformance");
+    src.blank();
+    src.import("import kotlin.math.max", "kotlin.math.max");
+    src.import("import kotlin.collections.*", "kotlin.collections");
+    src.import("import foo.Bar as Baz", "foo.Bar");
+    src.blank();
+    nested_braced(
+        src,
+        plan,
+        0,
+        None,
+        &|_, name| format!("class {} {{", name),
+        &|name| format!("fun {}() {{", name),
+        Some(&|name| format!("@{}", name)),
+    );
+    src.blank();
+    let f = braced_function(
+        src,
+        0,
+        format!(
+            "fun params({}: Int, {}: String = \"x\", vararg {}: String) {{",
+            plan.p(0),
+            plan.p(1),
+            plan.p(2)
+        ),
+        "params",
+        None,
+        &[],
+    );
+    src.parameters(f, &[plan.p(0), plan.p(1), plan.p(2)]);
+    braced_edge_cases(
+        src,
+        plan,
+        0,
+        None,
+        &|name| format!("fun {}() {{", name),
+        &|text| format!("val text = \"{}\"", text),
+        "if (true)",
+        "return",
+    );
+}
+
+fn write_php(src: &mut Source, plan: &Plan) {
+    src.line(0, "<?php");
+    src.line(0, "namespace Conformance;");
+    src.blank();
+    src.import("use Foo\\Bar;", "Foo\\Bar");
+    src.import("use Foo\\Baz as Qux;", "Foo\\Baz");
+    src.import("use function Foo\\helper;", "Foo\\helper");
+    // PHP has no nested classes: one class per level
+    for (depth, (ty, method)) in plan.types.iter().zip(&plan.methods).enumerate() {
+        src.blank();
+        if depth == 0 {
+            src.line(0, format!("#[{}]", plan.decorator));
+        }
+        let class = src.open(
+            0,
+            format!("class {} {{", ty),
+            NodeKind::Class,
+            ty.to_string(),
+            None,
+        );
+        if depth == 0 {
+            src.decorate(class, plan.decorator);
+        }
+        let name = format!("{}.{}", ty, method);
+        let m = src.open(
+            1,
+            format!("public function {}() {{", method),
+            NodeKind::Function,
+            name,
+            Some(ty),
+        );
+        src.close(m, 1, "}");
+        src.close(class, 0, "}");
+    }
+    src.blank();
+    let f = braced_function(
+        src,
+        0,
+        format!(
+            "function params(${}, int ${} = 1, &${}, ...${}) {{",
+            plan.p(0),
+            plan.p(1),
+            plan.p(2),
+            plan.p(3)
+        ),
+        "params",
+        None,
+        &[],
+    );
+    src.parameters(f, &[plan.p(0), plan.p(1), plan.p(2), plan.p(3)]);
+    braced_edge_cases(
+        src,
+        plan,
+        0,
+        None,
+        &|name| format!("function {}() {{", name),
+        &|text| format!("$text = \"{}\";", text),
+        "if (true)",
+        "return;",
+    );
+}
+
+fn write_python(src: &mut Source, plan: &Plan) {
+    src.import("import os", "os");
+    src.import("import os.path as osp", "os.path");
+    src.import("from typing import List, Dict as D", "typing");
+    src.import("from . import sibling", ".");
+    src.import("from .pkg.mod import *", ".pkg.mod");
+    src.blank();
+
+    fn level(src: &mut Source, plan: &Plan, depth: usize, parent: Option<&str>) {
+        let name = qualify(parent, plan.types[depth]);
+        let innermost = depth + 1 == plan.types.len();
+        if depth == 0 {
+            src.line(depth, format!("@{}", plan.decorator.to_lowercase()));
+        }
+        let class = src.open(
+            depth,
+            format!("class {}:", plan.types[depth]),
+            NodeKind::Class,
+            name.clone(),
+            parent,
+        );
+        if depth == 0 {
+            src.decorate(class, &plan.decorator.to_lowercase());
+        }
+        if !innermost {
+            level(src, plan, depth + 1, Some(&name));
+        } else {
+            src.line(depth + 1, format!("@{}", plan.decorator.to_lowercase()));
+        }
+        let m = src.open(
+            depth + 1,
+            format!("def {}(self):", plan.methods[depth]),
+            NodeKind::Function,
+            qualify(Some(&name), plan.methods[depth]),
+            Some(&name),
+        );
+        if innermost {
+            src.decorate(m, &plan.decorator.to_lowercase());
+        }
+        src.close(m, depth + 2, "pass");
+        src.ends(class);
+    }
+    level(src, plan, 0, None);
+
+    src.blank();
+    let f = src.open(
+        0,
+        format!(
+            "def params({}, {}: int, {}=1, *{}, {}, {}: str = \"x\", **{}) -> None:",
+            plan.p(0),
+            plan.p(1),
+            plan.p(2),
+            plan.p(3),
+            plan.p(4),
+            plan.p(5),
+            plan.p(6)
+        ),
+        NodeKind::Function,
+        "params".into(),
+        None,
+    );
+    src.close(f, 1, "pass");
+    src.parameters(f, &plan.params);
+
+    src.blank();
+    let f = src.open(
+        0,
+        format!("def {}():", plan.unicode),
+        NodeKind::Function,
+        plan.unicode.into(),
+        None,
+    );
+    src.close(f, 1, "pass");
+    src.blank();
+    let f = src.open(0, "def blob():", NodeKind::Function, "blob".into(), None);
+    src.close(f, 1, format!("return \"{}\"", plan.literal));
+    src.blank();
+    let f = src.open(
+        0,
+        "def deep(flag):",
+        NodeKind::Function,
+        "deep".into(),
+        None,
+    );
+    src.parameters(f, &["flag"]);
+    for level in 0..plan.blocks {
+        src.line(level + 1, "if flag:");
+    }
+    src.close(f, plan.blocks + 1, "pass");
+}
+
+fn write_ruby(src: &mut Source, plan: &Plan) {
+    src.import("require 'json'", "json");
+    src.import("require_relative 'lib/helper'", "lib/helper");
+    src.blank();
+
+    fn level(src: &mut Source, plan: &Plan, depth: usize, parent: Option<&str>) {
+        let name = qualify(parent, plan.types[depth]);
+        let class = src.open(
+            depth,
+            format!("class {}", plan.types[depth]),
+            NodeKind::Class,
+            name.clone(),
+            parent,
+        );
+        if depth + 1 < plan.types.len() {
+            level(src, plan, depth + 1, Some(&name));
+        }
+        let m = src.open(
+            depth + 1,
+            format!("def {}", plan.methods[depth]),
+            NodeKind::Function,
+            qualify(Some(&name), plan.methods[depth]),
+            Some(&name),
+        );
+        src.close(m, depth + 1, "end");
+        src.close(class, depth, "end");
+    }
+    level(src, plan, 0, None);
+
+    src.blank();
+    let f = src.open(
+        0,
+        format!(
+            "def params({}, {} = 1, *{}, {}:, {}: 2, **{}, &{})",
+            plan.p(0),
+            plan.p(1),
+            plan.p(2),
+            plan.p(3),
+            plan.p(4),
+            plan.p(5),
+            plan.p(6)
+        ),
+        NodeKind::Function,
+        "params".into(),
+        None,
+    );
+    src.close(f, 0, "end");
+    src.parameters(f, &plan.params);
+    src.blank();
+    let f = src.open(
+        0,
+        format!("def {}", plan.unicode),
+        NodeKind::Function,
+        plan.unicode.into(),
+        None,
+    );
+    src.close(f, 0, "end");
+    src.blank();
+    let f = src.open(0, "def blob", NodeKind::Function, "blob".into(), None);
+    src.line(1, format!("\"{}\"", plan.literal));
+    src.close(f, 0, "end");
+    src.blank();
+    let f = src.open(0, "def deep", NodeKind::Function, "deep".into(), None);
+    src.blocks(1, plan.blocks, "if true", "end");
+    src.close(f, 0, "end");
+}
+
+fn write_rust(src: &mut Source, plan: &Plan) {
+    src.import("use std::fmt;", "std");
+    src.import(
+        "use std::collections::{HashMap, HashSet};",
+        "std::collections",
+    );
+    src.import("use crate::config::Settings as Config;", "crate::config");
+    src.import("use super::*;", "super");
+
+    fn level(src: &mut Source, plan: &Plan, depth: usize, parent: Option<&str>) {
+        let ty = plan.types[depth];
+        let innermost = depth + 1 == plan.types.len();
+        src.blank();
+        if innermost {
+            // The innermost level is the type the modules around it hold
+            let name = qualify(parent, ty);
+            src.line(depth, "#[derive(Debug)]");
+            let s = src.open(
+                depth,
+                format!("pub struct {} {{", ty),
+                NodeKind::Class,
+                name.clone(),
+                parent,
+            );
+            src.decorate(s, "derive");
+            src.close(s, depth, "}");
+            src.blank();
+            src.line(depth, format!("impl {} {{", ty));
+            src.line(depth + 1, format!("#[{}]", plan.decorator.to_lowercase()));
+            let m = src.open(
+                depth + 1,
+                format!("pub fn {}(&self) {{", plan.methods[depth]),
+                NodeKind::Function,
+                qualify(Some(&name), plan.methods[depth]),
+                parent,
+            );
+            src.decorate(m, &plan.decorator.to_lowercase());
+            src.close(m, depth + 1, "}");
+            src.line(depth, "}");
+            return;
+        }
+        let name = qualify(parent, &ty.to_lowercase());
+        let module = src.open(
+            depth,
+            format!("pub mod {} {{", ty.to_lowercase()),
+            NodeKind::Module,
+            name.clone(),
+            parent,
+        );
+        level(src, plan, depth + 1, Some(&name));
+        src.close(module, depth, "}");
+    }
+    level(src, plan, 0, None);
+
+    src.blank();
+    let f = braced_function(
+        src,
+        0,
+        format!(
+            "fn params({}: i32, mut {}: String, {}: &[u8], {}: impl Fn()) {{",
+            plan.p(0),
+            plan.p(1),
+            plan.p(2),
+            plan.p(3)
+        ),
+        "params",
+        None,
+        &[],
+    );
+    src.parameters(f, &[plan.p(0), plan.p(1), plan.p(2), plan.p(3)]);
+    braced_edge_cases(
+        src,
+        plan,
+        0,
+        None,
+        &|name| format!("fn {}() {{", name),
+        &|text| format!("let _text = \"{}\";", text),
+        "if true",
+        "return;",
+    );
+}
+
+fn write_swift(src: &mut Source, plan: &Plan) {
+    src.import("import Foundation", "Foundation");
+    src.import("import struct Geometry.Point", "Geometry.Point");
+    src.import("@testable import Conformance", "Conformance");
+    src.blank();
+    nested_braced(
+        src,
+        plan,
+        0,
+        None,
+        &|_, name| format!("class {} {{", name),
+        &|name| format!("func {}() {{", name),
+        Some(&|name| format!("@{}", name)),
+    );
+    src.blank();
+    let f = braced_function(
+        src,
+        0,
+        format!(
+            "func params(_ {}: Int, {}: String = \"x\", label {}: Int, {}: Int...) {{",
+            plan.p(0),
+            plan.p(1),
+            plan.p(2),
+            plan.p(3)
+        ),
+        "params",
+        None,
+        &[],
+    );
+    src.parameters(f, &[plan.p(0), plan.p(1), plan.p(2), plan.p(3)]);
+    braced_edge_cases(
+        src,
+        plan,
+        0,
+        None,
+        &|name| format!("func {}() {{", name),
+        &|text| format!("let text = \"{}\"", text),
+        "if true",
+        "return",
+    );
+}
+
+fn write_typescript(src: &mut Source, plan: &Plan) {
+    src.import("import fs from \"fs\";", "fs");
+    src.import("import * as path from \"path\";", "path");
+    src.import("import { read, write as put } from \"./io\";", "./io");
+    src.import("import type { Options } from \"./types\";", "./types");
+    src.import("import \"./polyfill\";", "./polyfill");
+    src.import("const legacy = require(\"./legacy\");", "./legacy");
+    src.blank();
+    // Nesting is by namespaces, the innermost holding the class
+    let mut open = Vec::new();
+    let mut parent: Option<String> = None;
+    let last = plan.types.len() - 1;
+    for (depth, ty) in plan.types[..last].iter().enumerate() {
+        let name = qualify(parent.as_deref(), ty);
+        open.push(src.open(
+            depth,
+            format!("export namespace {} {{", ty),
+            NodeKind::Module,
+            name.clone(),
+            parent.as_deref(),
+        ));
+        parent = Some(name);
+    }
+    src.line(last, format!("@{}()", plan.decorator));
+    let name = qualify(parent.as_deref(), plan.types[last]);
+    let class = src.open(
+        last,
+        format!("export class {} {{", plan.types[last]),
+        NodeKind::Class,
+        name.clone(),
+        parent.as_deref(),
+    );
+    src.decorate(class, plan.decorator);
+    src.line(last + 1, format!("@{}()", plan.decorator));
+    let m = src.open(
+        last + 1,
+        format!("{}(): void {{}}", plan.methods[last]),
+        NodeKind::Function,
+        qualify(Some(&name), plan.methods[last]),
+        Some(&name),
+    );
+    src.decorate(m, plan.decorator);
+    src.close(class, last, "}");
+    for (depth, module) in open.into_iter().enumerate().rev() {
+        src.close(module, depth, "}");
+    }
+    src.blank();
+    let f = braced_function(
+        src,
+        0,
+        format!(
+            "function params({}: number, {}?: string, {} = 1, ...{}: string[]): void {{",
+            plan.p(0),
+            plan.p(1),
+            plan.p(2),
+            plan.p(3)
+        ),
+        "params",
+        None,
+        &[],
+    );
+    src.parameters(f, &[plan.p(0), plan.p(1), plan.p(2), plan.p(3)]);
+    braced_edge_cases(
+        src,
+        plan,
+        0,
+        None,
+        &|name| format!("function {}(): void {{", name),
+        &|text| format!("const text = \"{}\";", text),
+        "if (true)",
+        "return;",
+    );
+}
@@ -1,8 +1,15 @@
 //! C# language parser using Tree-sitter
-
+//!
+//! Top-level statements (a `Program.cs` without `Main`) become one
+//! [`ENTRY_POINT`] function spanning them. Calls that do not resolve in the
+//! file are recorded with the type they are made on, and `partial` types are
+//! tagged so their declarations can be merged across files (see
+//! [`super::csharp_partials`]).
+
+use super::docs::{self, DocStyle};
 use super::{
-    build_function_nodes_map, build_imports_map, collect_import_state, resolve_import_call,
-    LanguageParser, ParseError, ParseState, UnresolvedCall,
+    build_function_nodes_map, collect_import_state, csharp_partials, LanguageParser, ParseError,
+    ParseState, UnresolvedCall,
 };
 use crate::graph::{
     CodeGraph, Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeId, NodeKind, Parameter,
@@ -11,11 +18,14 @@ use std::collections::HashMap;
 use std::path::Path;
 use tree_sitter::{Parser, Tree, TreeCursor};
 
+/// Name of the function synthesized for a file's top-level statements, as
+/// the compiler names it
+pub const ENTRY_POINT: &str = "Program.<Main>$";
+
 /// Immutable context threaded through the cross-file-call AST walker.
 struct CsXfCallCtx<'a> {
     source: &'a str,
     function_nodes: &'a HashMap<String, NodeId>,
-    imports_map: &'a HashMap<String, String>,
     file_path: &'a Path,
 }
 
@@ -26,6 +36,8 @@ struct ExtractCtx<'a> {
     graph: &'a mut CodeGraph,
     function_nodes: HashMap<String, NodeId>,
     node_ids: Vec<NodeId>,
+    /// Namespace of the declarations being extracted
+    namespace: Option<String>,
 }
 
 /// C# language parser
@@ -83,6 +95,7 @@ impl CSharpParser {
             0,
             NodeData::File {
                 language: "csharp".to_string(),
+                code_kind: crate::code_kind::classify(file_path, graph.root_path(), source),
             },
         );
         let file_node_id = graph.add_node(file_node);
@@ -93,9 +106,11 @@ impl CSharpParser {
             graph,
             function_nodes: HashMap::new(),
             node_ids: Vec::new(),
+            namespace: None,
         };
 
         // First pass: extract top-level definitions
+        let mut statements = Vec::new();
         for child in root_node.children(&mut cursor) {
             match child.kind() {
                 "using_directive" => {
@@ -106,9 +121,16 @@ impl CSharpParser {
                     }
                 }
                 "namespace_declaration" => {
-                    if let Some(body) = child.child_by_field_name("body") {
-                        self.process_declarations(&body, &mut ctx, file_node_id);
-                    }
+                    self.extract_namespace(&child, &mut ctx, file_node_id);
+                }
+                "file_scoped_namespace_declaration" => {
+                    // Applies to every declaration after it in the file
+                    ctx.namespace = child
+                        .child_by_field_name("name")
+                        .map(|name| extract_qualified_name(&name, ctx.source));
+                }
+                "global_statement" => {
+                    statements.push(child);
                 }
                 "class_declaration" => {
                     self.extract_class(&child, &mut ctx, file_node_id, None);
@@ -129,6 +151,27 @@ impl CSharpParser {
             }
         }
 
+        if let (Some(first), Some(last)) = (statements.first(), statements.last()) {
+            let mut entry = Node::new(
+                NodeKind::Function,
+                ENTRY_POINT.to_string(),
+                file_path.to_path_buf(),
+                first.start_position().row + 1,
+                NodeData::Function {
+                    parameters: vec![Parameter {
+                        name: "args".to_string(),
+                        param_type: Some("string[]".to_string()),
+                        default_value: None,
+                    }],
+                    return_type: None,
+                },
+            );
+            entry.set_end_line(last.end_position().row + 1);
+            let entry_id = ctx.graph.add_node(entry);
+            ctx.function_nodes.insert(ENTRY_POINT.to_string(), entry_id);
+            ctx.node_ids.push(entry_id);
+        }
+
         // Second pass: extract function calls to build call graph
         let mut call_cursor = root_node.walk();
         self.extract_calls_recursive(
@@ -168,15 +211,27 @@ impl CSharpParser {
                     self.extract_record(&child, ctx, parent_id, None);
                 }
                 "namespace_declaration" => {
-                    if let Some(body) = child.child_by_field_name("body") {
-                        self.process_declarations(&body, ctx, parent_id);
-                    }
+                    self.extract_namespace(&child, ctx, parent_id);
                 }
                 _ => {}
             }
         }
     }
 
+    /// Process a block-scoped namespace, nested in the current one
+    fn extract_namespace(&self, node: &tree_sitter::Node, ctx: &mut ExtractCtx, parent_id: NodeId) {
+        let Some(body) = node.child_by_field_name("body") else {
+            return;
+        };
+        let outer = ctx.namespace.clone();
+        if let Some(name) = node.child_by_field_name("name") {
+            let name = extract_qualified_name(&name, ctx.source);
+            ctx.namespace = Some(qualify_name(outer.as_deref(), &name));
+        }
+        self.process_declarations(&body, ctx, parent_id);
+        ctx.namespace = outer;
+    }
+
     fn extract_class(
         &self,
         node: &tree_sitter::Node,
@@ -218,6 +273,10 @@ impl CSharpParser {
             );
         }
 
+        if has_modifiers(node, ctx.source, &["partial"]) {
+            decorators.push(csharp_partials::marker(ctx.namespace.as_deref()));
+        }
+
         let mut class_node = Node::new(
             NodeKind::Class,
             qualified_name.clone(),
@@ -284,6 +343,10 @@ impl CSharpParser {
             );
         }
 
+        if has_modifiers(node, ctx.source, &["partial"]) {
+            decorators.push(csharp_partials::marker(ctx.namespace.as_deref()));
+        }
+
         let mut struct_node = Node::new(
             NodeKind::Class,
             qualified_name.clone(),
@@ -521,6 +584,9 @@ impl CSharpParser {
         if !type_params.is_empty() {
             record_node.set_type_parameters(type_params);
         }
+        if has_modifiers(node, ctx.source, &["partial"]) {
+            record_node.set_decorators(vec![csharp_partials::marker(ctx.namespace.as_deref())]);
+        }
 
         let record_id = ctx.graph.add_node(record_node);
         ctx.graph
@@ -719,6 +785,8 @@ impl CSharpParser {
             || (has_modifiers(node, ctx.source, &["static"])
                 && has_modifiers(node, ctx.source, &["readonly"]));
 
+        let decorators = extract_decorators(node, ctx.source);
+
         // Iterate over variable_declarator children
         let mut cursor = var_decl.walk();
         for child in var_decl.children(&mut cursor) {
@@ -739,7 +807,7 @@ impl CSharpParser {
                 };
 
                 if let Some(name) = name {
-                    let var_node = Node::new(
+                    let mut var_node = Node::new(
                         NodeKind::Variable,
                         name.clone(),
                         ctx.file_path.to_path_buf(),
@@ -749,6 +817,9 @@ impl CSharpParser {
                             is_constant,
                         },
                     );
+                    if !decorators.is_empty() {
+                        var_node.set_decorators(decorators.clone());
+                    }
                     let var_id = ctx.graph.add_node(var_node);
                     results.push((var_id, name));
                 }
@@ -813,7 +884,6 @@ impl CSharpParser {
         source: &str,
         file_path: &Path,
         function_nodes: &HashMap<String, NodeId>,
-        imports_map: &HashMap<String, String>,
     ) -> Vec<UnresolvedCall> {
         let root = tree.root_node();
         let mut cursor = root.walk();
@@ -821,7 +891,6 @@ impl CSharpParser {
         let ctx = CsXfCallCtx {
             source,
             function_nodes,
-            imports_map,
             file_path,
         };
         self.collect_xf_calls_recursive(&mut cursor, &ctx, None, &mut result);
@@ -836,42 +905,47 @@ impl CSharpParser {
         out: &mut Vec<UnresolvedCall>,
     ) {
         let node = cursor.node();
-
-        let new_context = match node.kind() {
-            "method_declaration" | "constructor_declaration" | "property_declaration" => {
-                if let Some(name_node) = node.child_by_field_name("name") {
-                    if let Ok(name) = name_node.utf8_text(ctx.source.as_bytes()) {
-                        let qualified = find_enclosing_class_method(cursor, name, ctx.source);
-                        ctx.function_nodes
-                            .get(&qualified)
-                            .copied()
-                            .or(current_function)
-                    } else {
-                        current_function
-                    }
-                } else {
-                    current_function
-                }
-            }
-            _ => current_function,
+        let new_context =
+            enclosing_function(cursor, ctx.source, ctx.function_nodes, current_function);
+
+        // Calls that do not resolve in this file, keyed by the type they are
+        // made on: `Type.Method()`, `this.Method()` / `Method()` inside a type
+        // (another declaration of a partial type), `new Type()`
+        let target = match node.kind() {
+            "invocation_expression" => extract_call_target(&node, ctx.source)
+                .filter(|callee| resolve_local(callee, ctx.function_nodes).is_none())
+                .and_then(|callee| {
+                    let (receiver, member) = match callee.rsplit_once('.') {
+                        Some(("this", member)) => (None, member),
+                        Some((receiver, member)) => (Some(receiver), member),
+                        None => (None, callee.as_str()),
+                    };
+                    let owner = match receiver {
+                        Some(receiver) if is_type_name(receiver) => receiver.to_string(),
+                        Some(_) => return None,
+                        None => find_enclosing_class_method(cursor, member, ctx.source)
+                            .rsplit_once('.')?
+                            .0
+                            .to_string(),
+                    };
+                    Some((owner, member.to_string()))
+                }),
+            "object_creation_expression" => created_type(&node, ctx.source)
+                .filter(|ty| resolve_creation(ty, ctx.function_nodes).is_none())
+                .map(|ty| {
+                    let member = ty.rsplit('.').next().unwrap_or(&ty).to_string();
+                    (ty, member)
+                }),
+            _ => None,
         };
-
-        if node.kind() == "invocation_expression" {
-            if let (Some(caller), Some(callee_full)) =
-                (new_context, extract_call_target(&node, ctx.source))
-            {
-                if let Some((module, callee_name)) =
-                    resolve_import_call(&callee_full, ctx.imports_map, ctx.function_nodes)
-                {
-                    out.push(UnresolvedCall {
-                        caller_node_id: caller,
-                        callee_name,
-                        module_specifier: module,
-                        call_line: node.start_position().row + 1,
-                        importing_file: ctx.file_path.to_path_buf(),
-                    });
-                }
-            }
+        if let (Some(caller), Some((owner, member))) = (new_context, target) {
+            out.push(UnresolvedCall {
+                caller_node_id: caller,
+                callee_name: member,
+                module_specifier: owner,
+                call_line: node.start_position().row + 1,
+                importing_file: ctx.file_path.to_path_buf(),
+            });
         }
 
         if cursor.goto_first_child() {
@@ -896,54 +970,30 @@ impl CSharpParser {
         let node = cursor.node();
 
         // Update current function context
-        let new_context = match node.kind() {
-            "method_declaration" | "constructor_declaration" | "property_declaration" => {
-                if let Some(name_node) = node.child_by_field_name("name") {
-                    if let Ok(name) = name_node.utf8_text(source.as_bytes()) {
-                        let qualified = find_enclosing_class_method(cursor, name, source);
-                        function_nodes.get(&qualified).copied().or(current_function)
-                    } else {
-                        current_function
-                    }
-                } else {
-                    current_function
-                }
+        let new_context = enclosing_function(cursor, source, function_nodes, current_function);
+
+        // Look for invocation expressions (C# uses invocation_expression, not
+        // method_invocation) and `new T()`, a call of T's constructor
+        let callee = match node.kind() {
+            "invocation_expression" => extract_call_target(&node, source)
+                .and_then(|callee_name| resolve_local(&callee_name, function_nodes)),
+            "object_creation_expression" => {
+                created_type(&node, source).and_then(|ty| resolve_creation(&ty, function_nodes))
             }
-            _ => current_function,
+            _ => None,
         };
-
-        // Look for invocation expressions (C# uses invocation_expression, not method_invocation)
-        if node.kind() == "invocation_expression" {
-            if let Some(caller) = new_context {
-                if let Some(callee_name) = extract_call_target(&node, source) {
-                    let callee_id = function_nodes.get(&callee_name).copied().or_else(|| {
-                        // Wildcard fallback: try matching *.methodName
-                        if let Some(method_name) = callee_name.split('.').next_back() {
-                            let suffix = format!(".{}", method_name);
-                            function_nodes
-                                .iter()
-                                .find(|(k, _)| k.ends_with(&suffix) && *k != &callee_name)
-                                .map(|(_, &id)| id)
-                        } else {
-                            None
-                        }
-                    });
-
-                    if let Some(callee) = callee_id {
-                        graph.add_edge(
-                            caller,
-                            callee,
-                            Edge::with_metadata(
-                                EdgeKind::Calls,
-                                EdgeMetadata::Call {
-                                    line: node.start_position().row + 1,
-                                    is_direct: true,
-                                },
-                            ),
-                        );
-                    }
-                }
-            }
+        if let (Some(caller), Some(callee)) = (new_context, callee) {
+            graph.add_edge(
+                caller,
+                callee,
+                Edge::with_metadata(
+                    EdgeKind::Calls,
+                    EdgeMetadata::Call {
+                        line: node.start_position().row + 1,
+                        is_direct: true,
+                    },
+                ),
+            );
         }
 
         // Recurse into children
@@ -968,6 +1018,10 @@ impl LanguageParser for CSharpParser {
         &[".cs"]
     }
 
+    fn doc_style(&self) -> Option<DocStyle> {
+        Some(DocStyle::CSharp)
+    }
+
     fn parse_file(
         &self,
         file_path: &Path,
@@ -984,31 +1038,26 @@ impl LanguageParser for CSharpParser {
         graph: &mut CodeGraph,
     ) -> Result<Vec<NodeId>, ParseError> {
         let tree = self.parse_tree(source)?;
-        Ok(self.extract_nodes(&tree, source, file_path, graph))
+        let ids = self.extract_nodes(&tree, source, file_path, graph);
+        docs::record(graph, file_path, source, DocStyle::CSharp);
+        Ok(ids)
     }
 
-    fn parse_file_with_state(
+    fn parse_source_with_state(
         &self,
+        source: &str,
         file_path: &Path,
         graph: &mut CodeGraph,
     ) -> Result<(Vec<NodeId>, ParseState), ParseError> {
-        let source = std::fs::read_to_string(file_path)?;
-        let tree = self.parse_tree(&source)?;
-        let ids = self.extract_nodes(&tree, &source, file_path, graph);
+        let tree = self.parse_tree(source)?;
+        let ids = self.extract_nodes(&tree, source, file_path, graph);
+        docs::record(graph, file_path, source, DocStyle::CSharp);
 
         let mut state = collect_import_state(graph, file_path);
 
-        let imports_map = build_imports_map(&state);
-        if !imports_map.is_empty() {
-            let function_nodes = build_function_nodes_map(graph, file_path);
-            state.unresolved_calls = self.collect_cross_file_calls(
-                &tree,
-                &source,
-                file_path,
-                &function_nodes,
-                &imports_map,
-            );
-        }
+        let function_nodes = build_function_nodes_map(graph, file_path);
+        state.unresolved_calls =
+            self.collect_cross_file_calls(&tree, source, file_path, &function_nodes);
 
         Ok((ids, state))
     }
@@ -1219,6 +1268,69 @@ fn extract_call_target(node: &tree_sitter::Node, source: &str) -> Option<String>
     }
 }
 
+/// The function a node belongs to: a method, constructor or property it
+/// declares, the [`ENTRY_POINT`] for top-level statements, else `current`
+fn enclosing_function(
+    cursor: &TreeCursor,
+    source: &str,
+    function_nodes: &HashMap<String, NodeId>,
+    current: Option<NodeId>,
+) -> Option<NodeId> {
+    let node = cursor.node();
+    match node.kind() {
+        "method_declaration" | "constructor_declaration" | "property_declaration" => node
+            .child_by_field_name("name")
+            .and_then(|name_node| name_node.utf8_text(source.as_bytes()).ok())
+            .and_then(|name| {
+                let qualified = find_enclosing_class_method(cursor, name, source);
+                function_nodes.get(&qualified).copied()
+            })
+            .or(current),
+        "global_statement" => function_nodes.get(ENTRY_POINT).copied().or(current),
+        _ => current,
+    }
+}
+
+/// The function in this file a call target names, exactly or by method name
+fn resolve_local(callee_name: &str, function_nodes: &HashMap<String, NodeId>) -> Option<NodeId> {
+    function_nodes.get(callee_name).copied().or_else(|| {
+        // Wildcard fallback: try matching *.methodName
+        let method_name = callee_name.split('.').next_back()?;
+        let suffix = format!(".{}", method_name);
+        function_nodes
+            .iter()
+            .find(|(k, _)| k.ends_with(&suffix) && *k != callee_name)
+            .map(|(_, &id)| id)
+    })
+}
+
+/// The constructor `new T()` calls in this file, or T itself when it
+/// declares none
+fn resolve_creation(type_name: &str, function_nodes: &HashMap<String, NodeId>) -> Option<NodeId> {
+    let simple = type_name.rsplit('.').next().unwrap_or(type_name);
+    function_nodes
+        .get(&format!("{}.{}", type_name, simple))
+        .or_else(|| function_nodes.get(type_name))
+        .copied()
+}
+
+/// `new Models.Order<T>(...)` → `Models.Order`
+fn created_type(node: &tree_sitter::Node, source: &str) -> Option<String> {
+    let type_node = node.child_by_field_name("type")?;
+    let text = type_node.utf8_text(source.as_bytes()).ok()?;
+    let name = text.split('<').next()?.trim();
+    is_type_name(name).then(|| name.to_string())
+}
+
+/// Whether a call receiver reads as a (possibly namespace-qualified) type
+/// name: dotted identifiers, each capitalised (`order.Lines` is a member)
+fn is_type_name(text: &str) -> bool {
+    text.split('.').all(|part| {
+        part.chars().next().is_some_and(|c| c.is_uppercase())
+            && part.chars().all(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
 /// Check if a declaration has specific modifiers (e.g., ["static", "readonly"])
 fn has_modifiers(node: &tree_sitter::Node, source: &str, required: &[&str]) -> bool {
     let mut modifier_texts = Vec::new();
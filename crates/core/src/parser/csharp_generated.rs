@@ -0,0 +1,353 @@
+//! C# source generators: members that only exist in generated code
+//!
+//! `[GeneratedRegex]` and `[LoggerMessage]` methods are declared `partial`
+//! and implemented by a generator, `[ObservableProperty]` fields and
+//! `[RelayCommand]` methods get a property generated next to them, and gRPC
+//! services derive from a generated `Service.ServiceBase` the runtime
+//! instantiates. Before cross-file calls are resolved, [`link`] applies the
+//! `[parser.csharp.generated]` table (on top of the built-in one):
+//!
+//! - each generated member becomes a `Function` node of the enclosing type,
+//!   tagged `generated <generator>`, with a [`EdgeKind::References`] edge to
+//!   the member that produced it; a member the source already declares (the
+//!   `partial` method a regex generator implements) is only tagged
+//! - classes deriving from a generated base are tagged as entry points,
+//!   which the unused-export check treats as roots
+
+use crate::config::{CSharpGeneratorConfig, CSharpParserConfig};
+use crate::finding::Confidence;
+use crate::graph::{CodeGraph, Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeId, NodeKind};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use super::python_frameworks::ENTRY_POINT_PREFIX;
+
+/// Decorator prefix marking a member the named generator implements
+pub const GENERATED_PREFIX: &str = "generated ";
+
+/// A built-in generator: name, triggering attributes, generated bases, members
+type Builtin = (
+    &'static str,
+    &'static [&'static str],
+    &'static [&'static str],
+    &'static [&'static str],
+);
+
+const BUILTIN: &[Builtin] = &[
+    ("regex", &["GeneratedRegex"], &[], &["{name}"]),
+    ("logging", &["LoggerMessage"], &[], &["{name}"]),
+    ("mvvm_property", &["ObservableProperty"], &[], &["{Name}"]),
+    ("mvvm_command", &["RelayCommand"], &[], &["{Name}Command"]),
+    ("grpc", &[], &["*.*Base"], &[]),
+];
+
+/// The effective generator table
+#[derive(Debug, Clone)]
+pub struct Generators {
+    generators: Vec<Generator>,
+}
+
+#[derive(Debug, Clone)]
+struct Generator {
+    name: String,
+    attributes: Vec<Regex>,
+    bases: Vec<Regex>,
+    members: Vec<String>,
+}
+
+impl Generators {
+    /// The built-in table only
+    pub fn builtin() -> Self {
+        Self::from_config(&CSharpParserConfig::default())
+    }
+
+    /// The built-in table with `[parser.csharp.generated]` applied: entries
+    /// with a built-in name replace it, others are added
+    pub fn from_config(config: &CSharpParserConfig) -> Self {
+        let mut table: BTreeMap<String, CSharpGeneratorConfig> = BUILTIN
+            .iter()
+            .map(|(name, attributes, bases, members)| {
+                let strings = |list: &[&str]| list.iter().map(|s| s.to_string()).collect();
+                (
+                    name.to_string(),
+                    CSharpGeneratorConfig {
+                        attributes: strings(attributes),
+                        bases: strings(bases),
+                        members: strings(members),
+                    },
+                )
+            })
+            .collect();
+        table.extend(config.generated.clone());
+
+        let globs = |patterns: &[String]| patterns.iter().filter_map(|p| glob(p)).collect();
+        let generators = table
+            .into_iter()
+            .map(|(name, entry)| Generator {
+                name,
+                attributes: globs(&entry.attributes),
+                bases: globs(&entry.bases),
+                members: entry.members,
+            })
+            .collect();
+        Self { generators }
+    }
+
+    /// The generator an attribute (without brackets or arguments) triggers
+    fn triggered_by(&self, attribute: &str) -> Option<&Generator> {
+        let candidates = attribute_names(attribute);
+        self.generators.iter().find(|g| {
+            g.attributes
+                .iter()
+                .any(|re| candidates.iter().any(|c| re.is_match(c)))
+        })
+    }
+
+    /// The generator that provides `base`, a base type of some class
+    fn providing(&self, base: &str) -> Option<&Generator> {
+        let base = base.split('<').next().unwrap_or(base).trim();
+        self.generators
+            .iter()
+            .find(|g| g.bases.iter().any(|re| re.is_match(base)))
+    }
+}
+
+impl Default for Generators {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+/// The generator that implements `node`, if it was generated
+pub fn generator(node: &Node) -> Option<&str> {
+    node.decorators()
+        .iter()
+        .find_map(|d| d.strip_prefix(GENERATED_PREFIX))
+}
+
+/// `*.*Base` → `^.*\..*Base$`
+fn glob(pattern: &str) -> Option<Regex> {
+    let body = pattern
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(".*");
+    Regex::new(&format!("^{}$", body)).ok()
+}
+
+/// The spellings an attribute may be matched under:
+/// `Mvvm.ObservablePropertyAttribute` → itself, `ObservablePropertyAttribute`,
+/// `Mvvm.ObservableProperty` and `ObservableProperty`
+fn attribute_names(attribute: &str) -> Vec<&str> {
+    let short = attribute.rsplit('.').next().unwrap_or(attribute);
+    let mut names = vec![attribute, short];
+    for name in [attribute, short] {
+        if let Some(stripped) = name.strip_suffix("Attribute").filter(|s| !s.is_empty()) {
+            names.push(stripped);
+        }
+    }
+    names
+}
+
+/// Expand a member template for the member `name`
+fn expand(template: &str, name: &str) -> String {
+    let bare = name
+        .strip_prefix("m_")
+        .or_else(|| name.strip_prefix('_'))
+        .unwrap_or(name);
+    let bare = bare
+        .strip_suffix("Async")
+        .filter(|s| !s.is_empty())
+        .unwrap_or(bare);
+    let mut chars = bare.chars();
+    let capitalised: String = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    };
+    template
+        .replace("{name}", name)
+        .replace("{Name}", &capitalised)
+}
+
+fn is_csharp(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("cs")
+}
+
+/// A member or type that triggers a generator
+struct Trigger {
+    /// The annotated node
+    source: NodeId,
+    generator: String,
+    /// Qualified name of the type that receives the generated members
+    owner: String,
+    /// Name the member templates expand
+    member: String,
+    file: PathBuf,
+    line: usize,
+    members: Vec<String>,
+}
+
+/// Synthesize generated members and tag generated-base subclasses as entry
+/// points. Safe to run again over an already linked graph.
+pub fn link(graph: &mut CodeGraph, generators: &Generators) {
+    let mut triggers: Vec<Trigger> = Vec::new();
+    let mut entry_points: Vec<(NodeId, String)> = Vec::new();
+    let mut existing: HashMap<(PathBuf, String), NodeId> = HashMap::new();
+    let mut owners: HashMap<(PathBuf, String), NodeId> = HashMap::new();
+    {
+        // Classes by file, for finding the type that declares a field
+        let mut classes: HashMap<&Path, Vec<(usize, usize, &str)>> = HashMap::new();
+        for (id, node) in graph.nodes() {
+            if !is_csharp(node.file_path()) {
+                continue;
+            }
+            match node.kind() {
+                NodeKind::Function => {
+                    existing.insert((node.file_path().clone(), node.name().to_string()), id);
+                }
+                NodeKind::Class => {
+                    owners.insert((node.file_path().clone(), node.name().to_string()), id);
+                    let end = node.end_line().unwrap_or(node.line());
+                    classes.entry(node.file_path()).or_default().push((
+                        node.line(),
+                        end,
+                        node.name(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        for (id, node) in graph.nodes() {
+            if !is_csharp(node.file_path()) {
+                continue;
+            }
+            let (owner, member) = match node.kind() {
+                NodeKind::Function => match node.name().rsplit_once('.') {
+                    Some((owner, member)) => (owner.to_string(), member.to_string()),
+                    None => continue,
+                },
+                NodeKind::Class => {
+                    let NodeData::Class { base_classes, .. } = node.data() else {
+                        continue;
+                    };
+                    let provider = base_classes.iter().find_map(|b| generators.providing(b));
+                    if let Some(provider) = provider {
+                        if !node.decorators().iter().any(|d| {
+                            d.strip_prefix(ENTRY_POINT_PREFIX) == Some(provider.name.as_str())
+                        }) {
+                            entry_points.push((id, provider.name.clone()));
+                        }
+                        triggers.push(Trigger {
+                            source: id,
+                            generator: provider.name.clone(),
+                            owner: node.name().to_string(),
+                            member: simple_name(node.name()).to_string(),
+                            file: node.file_path().clone(),
+                            line: node.line(),
+                            members: provider.members.clone(),
+                        });
+                    }
+                    (
+                        node.name().to_string(),
+                        simple_name(node.name()).to_string(),
+                    )
+                }
+                NodeKind::Variable => {
+                    // Innermost class whose span holds the field
+                    let owner = classes
+                        .get(node.file_path().as_path())
+                        .into_iter()
+                        .flatten()
+                        .filter(|(start, end, _)| (*start..=*end).contains(&node.line()))
+                        .max_by_key(|(start, _, _)| *start);
+                    match owner {
+                        Some((_, _, owner)) => (owner.to_string(), node.name().to_string()),
+                        None => continue,
+                    }
+                }
+                _ => continue,
+            };
+            for decorator in node.decorators() {
+                if let Some(generator) = generators.triggered_by(decorator) {
+                    triggers.push(Trigger {
+                        source: id,
+                        generator: generator.name.clone(),
+                        owner: owner.clone(),
+                        member: member.clone(),
+                        file: node.file_path().clone(),
+                        line: node.line(),
+                        members: generator.members.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    for (id, generator) in entry_points {
+        tag(graph, id, format!("{}{}", ENTRY_POINT_PREFIX, generator));
+    }
+    for trigger in triggers {
+        for template in &trigger.members {
+            let member = expand(template, &trigger.member);
+            let name = format!("{}.{}", trigger.owner, member);
+            let tag_text = format!("{}{}", GENERATED_PREFIX, trigger.generator);
+            if let Some(&id) = existing.get(&(trigger.file.clone(), name.clone())) {
+                tag(graph, id, tag_text);
+                continue;
+            }
+
+            let mut node = Node::new(
+                NodeKind::Function,
+                name.clone(),
+                trigger.file.clone(),
+                trigger.line,
+                NodeData::Function {
+                    parameters: Vec::new(),
+                    return_type: None,
+                },
+            );
+            node.set_end_line(trigger.line);
+            node.set_decorators(vec![tag_text]);
+            let id = graph.add_node(node);
+            graph.add_edge(
+                id,
+                trigger.source,
+                Edge::with_metadata(
+                    EdgeKind::References,
+                    EdgeMetadata::Reference {
+                        line: trigger.line,
+                        confidence: Confidence::High,
+                    },
+                ),
+            );
+            existing.insert((trigger.file.clone(), name), id);
+
+            let owner = owners.get(&(trigger.file.clone(), trigger.owner.clone()));
+            if let Some(NodeData::Class { methods, .. }) =
+                owner.and_then(|&o| graph.node_mut(o)).map(|n| n.data_mut())
+            {
+                if !methods.contains(&member) {
+                    methods.push(member);
+                }
+            }
+        }
+    }
+}
+
+/// `Outer.Inner` → `Inner`
+fn simple_name(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Add `decorator` to a node unless it already carries it
+fn tag(graph: &mut CodeGraph, id: NodeId, decorator: String) {
+    if let Some(node) = graph.node_mut(id) {
+        if !node.decorators().contains(&decorator) {
+            let mut decorators = node.decorators().to_vec();
+            decorators.push(decorator);
+            node.set_decorators(decorators);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! C# partial types: one logical type declared in several places
+//!
+//! The parser tags every `partial` class, struct and record with a
+//! `partial <namespace>` decorator. After cross-file resolution, [`merge`]
+//! folds the declarations of one type into a single canonical node: the one
+//! in the file named after the type (`Order.cs`), else the first by path and
+//! line:
+//!
+//! - every declaring file gets a [`EdgeKind::Contains`] edge to it
+//! - its methods, fields, base types and attributes are the union of all
+//!   declarations
+//! - edges to and from the other declarations move to it, and those nodes
+//!   are removed
+//!
+//! Members keep their own nodes in the file that declares them; calls
+//! between the halves resolve by type (see [`super::resolver`]).
+
+use crate::graph::{CodeGraph, EdgeKind, NodeData, NodeId, NodeKind};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Decorator marking a `partial` type; followed by its namespace, if any
+pub const PARTIAL_MARKER: &str = "partial";
+
+/// The decorator for a `partial` type declared in `namespace`
+pub fn marker(namespace: Option<&str>) -> String {
+    match namespace {
+        Some(namespace) => format!("{} {}", PARTIAL_MARKER, namespace),
+        None => PARTIAL_MARKER.to_string(),
+    }
+}
+
+/// The namespace of a `partial` type (`""` for the global one), or `None`
+/// for a type that is not partial
+fn namespace(decorators: &[String]) -> Option<&str> {
+    decorators.iter().find_map(|d| {
+        if d == PARTIAL_MARKER {
+            Some("")
+        } else {
+            d.strip_prefix(PARTIAL_MARKER)?.strip_prefix(' ')
+        }
+    })
+}
+
+fn is_csharp(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("cs")
+}
+
+type Declaration = (bool, PathBuf, usize, NodeId);
+
+/// Fold every partial type's declarations into one canonical node.
+///
+/// Removes nodes, so NodeIds held across this call are invalidated.
+pub fn merge(graph: &mut CodeGraph) {
+    // (namespace, name) → (not named after the file, path, line, id)
+    let mut groups: BTreeMap<(String, String), Vec<Declaration>> = BTreeMap::new();
+    for (id, node) in graph.nodes() {
+        if !matches!(node.kind(), NodeKind::Class) || !is_csharp(node.file_path()) {
+            continue;
+        }
+        if let Some(namespace) = namespace(node.decorators()) {
+            let simple = node.name().rsplit('.').next().unwrap_or(node.name());
+            let named_after = node.file_path().file_stem().and_then(|s| s.to_str()) == Some(simple);
+            groups
+                .entry((namespace.to_string(), node.name().to_string()))
+                .or_default()
+                .push((!named_after, node.file_path().clone(), node.line(), id));
+        }
+    }
+
+    let mut removed = Vec::new();
+    for mut declarations in groups.into_values() {
+        if declarations.len() < 2 {
+            continue;
+        }
+        declarations.sort();
+        let canonical = declarations[0].3;
+        for &(_, _, _, half) in &declarations[1..] {
+            fold(graph, canonical, half);
+            removed.push(half);
+        }
+    }
+
+    // Highest first: each removal moves the last node into the freed ID,
+    // which is never one still waiting to be removed
+    removed.sort();
+    for id in removed.into_iter().rev() {
+        graph.remove_node(id);
+    }
+}
+
+/// Move `half`'s members and edges onto `canonical`
+fn fold(graph: &mut CodeGraph, canonical: NodeId, half: NodeId) {
+    let Some(half_node) = graph.node(half).cloned() else {
+        return;
+    };
+    if let Some(node) = graph.node_mut(canonical) {
+        if let (
+            NodeData::Class {
+                base_classes,
+                methods,
+                fields,
+            },
+            NodeData::Class {
+                base_classes: more_bases,
+                methods: more_methods,
+                fields: more_fields,
+            },
+        ) = (node.data_mut(), half_node.data())
+        {
+            union(base_classes, more_bases);
+            union(methods, more_methods);
+            union(fields, more_fields);
+        }
+        let mut decorators = node.decorators().to_vec();
+        union(&mut decorators, half_node.decorators());
+        node.set_decorators(decorators);
+    }
+
+    let incoming: Vec<_> = graph
+        .edges_to(half)
+        .into_iter()
+        .filter(|(source, _)| *source != canonical)
+        .map(|(source, edge)| (source, edge.clone()))
+        .collect();
+    let outgoing: Vec<_> = graph
+        .edges_from(half)
+        .filter(|(target, _)| *target != canonical)
+        .map(|(target, edge)| (target, edge.clone()))
+        .collect();
+    for (source, edge) in incoming {
+        let contained = edge.kind() == &EdgeKind::Contains
+            && graph
+                .edges_from(source)
+                .any(|(t, e)| t == canonical && e.kind() == &EdgeKind::Contains);
+        if !contained {
+            graph.add_edge(source, canonical, edge);
+        }
+    }
+    for (target, edge) in outgoing {
+        graph.add_edge(canonical, target, edge);
+    }
+}
+
+fn union(into: &mut Vec<String>, more: &[String]) {
+    for item in more {
+        if !into.contains(item) {
+            into.push(item.clone());
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Dev-only packages declared by the manifest that owns a file
+//!
+//! An import of an external package is a dev import when the nearest
+//! manifest at or above the importing file declares the package only as a
+//! development dependency:
+//!
+//! | Files | Manifest | Dev sections |
+//! |-------|----------|--------------|
+//! | `.js` `.jsx` `.ts` `.tsx` `.mjs` `.cjs` | `package.json` | `devDependencies` |
+//! | `.py` | `pyproject.toml` | `[dependency-groups]`, `tool.poetry.dev-dependencies`, `tool.poetry.group.*.dependencies`, `tool.pdm.dev-dependencies` |
+//! | `.rs` | `Cargo.toml` | `dev-dependencies` (including `target.*`) |
+//!
+//! A package that is also a runtime dependency is not dev-only. Python
+//! modules are matched by their top-level name, so a distribution whose
+//! import name differs (`PyYAML` / `yaml`) is not recognised.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::lockfile::python::requirement_name;
+use crate::lockfile::Ecosystem;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Manifest {
+    PackageJson,
+    Pyproject,
+    Cargo,
+}
+
+impl Manifest {
+    fn of(file: &Path) -> Option<Self> {
+        match file.extension()?.to_str()? {
+            "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" => Some(Self::PackageJson),
+            "py" => Some(Self::Pyproject),
+            "rs" => Some(Self::Cargo),
+            _ => None,
+        }
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            Self::PackageJson => "package.json",
+            Self::Pyproject => "pyproject.toml",
+            Self::Cargo => "Cargo.toml",
+        }
+    }
+
+    /// The package an import specifier names, if it is external
+    fn package(self, specifier: &str) -> Option<String> {
+        match self {
+            Self::PackageJson => {
+                if specifier.starts_with(['.', '/']) || specifier.starts_with("node:") {
+                    return None;
+                }
+                let mut segments = specifier.split('/');
+                let first = segments.next()?;
+                if first.starts_with('@') {
+                    Some(format!("{}/{}", first, segments.next()?))
+                } else {
+                    Some(first.to_string())
+                }
+            }
+            Self::Pyproject => {
+                let first = specifier.split('.').next()?;
+                (!first.is_empty()).then(|| Ecosystem::PyPI.normalize_name(first))
+            }
+            Self::Cargo => {
+                let first = specifier.split("::").next()?.trim();
+                match first {
+                    "" | "crate" | "self" | "super" | "std" | "core" | "alloc" => None,
+                    _ => Some(first.replace('-', "_")),
+                }
+            }
+        }
+    }
+
+    /// Packages declared only as dev dependencies in `content`
+    fn dev_only(self, content: &str) -> HashSet<String> {
+        let (dev, runtime) = match self {
+            Self::PackageJson => package_json(content),
+            Self::Pyproject => pyproject(content),
+            Self::Cargo => cargo(content),
+        };
+        dev.difference(&runtime).cloned().collect()
+    }
+}
+
+/// Looks up dev-only packages, reading each manifest once
+pub(crate) struct DevDeps<'a> {
+    root: &'a Path,
+    /// Dev-only packages of the manifest in a directory; `None` when the
+    /// directory has no manifest of that kind
+    manifests: HashMap<(PathBuf, Manifest), Option<HashSet<String>>>,
+}
+
+impl<'a> DevDeps<'a> {
+    pub(crate) fn new(root: &'a Path) -> Self {
+        Self {
+            root,
+            manifests: HashMap::new(),
+        }
+    }
+
+    /// Whether `specifier`, imported by `file`, names a package its manifest
+    /// declares only for development
+    pub(crate) fn is_dev(&mut self, file: &Path, specifier: &str) -> bool {
+        let Some(manifest) = Manifest::of(file) else {
+            return false;
+        };
+        let Some(package) = manifest.package(specifier) else {
+            return false;
+        };
+        let mut dir = file.parent();
+        while let Some(current) = dir {
+            if let Some(dev) = self.manifest(current, manifest) {
+                return dev.contains(&package);
+            }
+            if current == self.root || !current.starts_with(self.root) {
+                break;
+            }
+            dir = current.parent();
+        }
+        false
+    }
+
+    fn manifest(&mut self, dir: &Path, manifest: Manifest) -> Option<&HashSet<String>> {
+        self.manifests
+            .entry((dir.to_path_buf(), manifest))
+            .or_insert_with(|| {
+                let content = std::fs::read_to_string(dir.join(manifest.file_name())).ok()?;
+                Some(manifest.dev_only(&content))
+            })
+            .as_ref()
+    }
+}
+
+fn package_json(content: &str) -> (HashSet<String>, HashSet<String>) {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Default::default();
+    };
+    let keys = |sections: &[&str]| -> HashSet<String> {
+        sections
+            .iter()
+            .filter_map(|s| json.get(*s)?.as_object())
+            .flat_map(|deps| deps.keys().cloned())
+            .collect()
+    };
+    (
+        keys(&["devDependencies"]),
+        keys(&["dependencies", "peerDependencies", "optionalDependencies"]),
+    )
+}
+
+fn pyproject(content: &str) -> (HashSet<String>, HashSet<String>) {
+    let Ok(doc) = content.parse::<toml::Table>() else {
+        return Default::default();
+    };
+    let normalize = |name: &str| Ecosystem::PyPI.normalize_name(name);
+    let table_keys = |table: Option<&toml::Value>| -> Vec<String> {
+        table
+            .and_then(|t| t.as_table())
+            .map(|t| t.keys().map(|k| normalize(k)).collect())
+            .unwrap_or_default()
+    };
+    // PEP 735 groups and PDM list requirement strings per group
+    let group_lists = |groups: Option<&toml::Value>| -> Vec<String> {
+        groups
+            .and_then(|g| g.as_table())
+            .into_iter()
+            .flat_map(|g| g.values())
+            .filter_map(|list| list.as_array())
+            .flatten()
+            .filter_map(|spec| spec.as_str())
+            .map(|spec| normalize(requirement_name(spec)))
+            .collect()
+    };
+    let poetry = doc.get("tool").and_then(|t| t.get("poetry"));
+
+    let mut dev: HashSet<String> = HashSet::new();
+    dev.extend(group_lists(doc.get("dependency-groups")));
+    dev.extend(group_lists(
+        doc.get("tool")
+            .and_then(|t| t.get("pdm"))
+            .and_then(|p| p.get("dev-dependencies")),
+    ));
+    dev.extend(table_keys(poetry.and_then(|p| p.get("dev-dependencies"))));
+    if let Some(groups) = poetry
+        .and_then(|p| p.get("group"))
+        .and_then(|g| g.as_table())
+    {
+        for group in groups.values() {
+            dev.extend(table_keys(group.get("dependencies")));
+        }
+    }
+
+    let mut runtime: HashSet<String> = doc
+        .get("project")
+        .and_then(|p| p.get("dependencies"))
+        .and_then(|d| d.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|spec| spec.as_str())
+        .map(|spec| normalize(requirement_name(spec)))
+        .collect();
+    runtime.extend(table_keys(poetry.and_then(|p| p.get("dependencies"))));
+    (dev, runtime)
+}
+
+fn cargo(content: &str) -> (HashSet<String>, HashSet<String>) {
+    let Ok(doc) = content.parse::<toml::Table>() else {
+        return Default::default();
+    };
+    // `[target.'cfg(..)'.dev-dependencies]` counts like the top-level section
+    let tables: Vec<&toml::Table> = std::iter::once(&doc)
+        .chain(
+            doc.get("target")
+                .and_then(|t| t.as_table())
+                .into_iter()
+                .flat_map(|t| t.values())
+                .filter_map(|t| t.as_table()),
+        )
+        .collect();
+    let keys = |sections: &[&str]| -> HashSet<String> {
+        tables
+            .iter()
+            .flat_map(|t| sections.iter().filter_map(|s| t.get(*s)?.as_table()))
+            .flat_map(|deps| deps.keys().map(|k| k.replace('-', "_")))
+            .collect()
+    };
+    (
+        keys(&["dev-dependencies"]),
+        keys(&["dependencies", "build-dependencies"]),
+    )
+}
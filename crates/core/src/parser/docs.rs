@@ -0,0 +1,172 @@
+//! Documentation comments: whether each declaration is documented
+//!
+//! Parsers that support it call [`record`] after extracting a file, which
+//! sets a [`Doc`] on every function, type and variable of the file from the
+//! source text around its line: the comment block immediately above the
+//! declaration (below nothing but attributes and decorators), or for Python
+//! the docstring opening its body. Nodes of parsers that don't call it stay
+//! [`Doc::Unrecorded`], which the `DOC` analyzer skips rather than report.
+
+use crate::graph::{CodeGraph, Doc, NodeKind};
+use std::path::Path;
+
+/// Documentation comment conventions of a language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocStyle {
+    /// A docstring as the first statement of the body; `#:` comments above
+    /// module variables
+    Python,
+    /// `///` lines or a `/** */` block
+    Rust,
+    /// The `//` or `/* */` comment block directly above
+    Go,
+    /// A `/** */` block (TypeScript, JavaScript, Java)
+    JsDoc,
+    /// `///` XML doc lines or a `/** */` block
+    CSharp,
+}
+
+/// Node kinds a documentation comment is recorded for
+const DOCUMENTABLE: &[NodeKind] = &[
+    NodeKind::Function,
+    NodeKind::Class,
+    NodeKind::Interface,
+    NodeKind::Type,
+    NodeKind::Variable,
+];
+
+/// Record the documentation comment of every declaration of `file_path`
+pub fn record(graph: &mut CodeGraph, file_path: &Path, source: &str, style: DocStyle) {
+    let lines: Vec<&str> = source.lines().collect();
+    for id in graph.find_nodes(file_path, None) {
+        let Some(node) = graph.node_mut(id) else {
+            continue;
+        };
+        if node.file_path() != file_path || !DOCUMENTABLE.contains(node.kind()) {
+            continue;
+        }
+        let line = node.line().saturating_sub(1);
+        let doc = match style {
+            DocStyle::Python if node.kind() == &NodeKind::Variable => {
+                comment_above(&lines, line, style).or_else(|| string_after(&lines, line))
+            }
+            DocStyle::Python => docstring(&lines, line, node.end_line()),
+            _ => comment_above(&lines, line, style),
+        };
+        node.set_doc(doc.map_or(Doc::Missing, Doc::Present));
+    }
+}
+
+/// The comment block ending right above line `at` (0-based), past any
+/// attribute, annotation and decorator lines, as its first line
+fn comment_above(lines: &[&str], at: usize, style: DocStyle) -> Option<String> {
+    let mut i = at;
+    while i > 0 && is_attribute(lines[i - 1].trim(), style) {
+        i -= 1;
+    }
+    let above = lines.get(i.checked_sub(1)?)?.trim();
+
+    if above.ends_with("*/") {
+        let opener = match style {
+            DocStyle::Go => "/*",
+            DocStyle::Python => return None,
+            _ => "/**",
+        };
+        let start = (0..i).rev().find(|&j| lines[j].contains("/*"))?;
+        let first = lines[start].trim();
+        if !first.starts_with(opener) || first.starts_with("/**/") {
+            return None;
+        }
+        return Some(first_line(lines[start..i].iter().map(|l| {
+            l.trim()
+                .trim_start_matches(opener)
+                .trim_end_matches("*/")
+                .trim_start_matches('*')
+        })));
+    }
+
+    let marker = match style {
+        DocStyle::Rust | DocStyle::CSharp => "///",
+        DocStyle::Go => "//",
+        DocStyle::Python => "#:",
+        DocStyle::JsDoc => return None,
+    };
+    let is_doc = |l: &str| {
+        let l = l.trim();
+        l.starts_with(marker) && !(style == DocStyle::Rust && l.starts_with("////"))
+    };
+    if !is_doc(above) {
+        return None;
+    }
+    let start = (0..i).rev().take_while(|&j| is_doc(lines[j])).last()?;
+    Some(first_line(
+        lines[start..i]
+            .iter()
+            .map(|l| l.trim().trim_start_matches(marker)),
+    ))
+}
+
+/// The first non-empty line of a comment's text, outside any C# `<summary>`
+/// tags
+fn first_line<'a>(text: impl Iterator<Item = &'a str>) -> String {
+    text.map(|l| {
+        l.trim()
+            .trim_start_matches("<summary>")
+            .trim_end_matches("</summary>")
+            .trim()
+    })
+    .find(|l| !l.is_empty())
+    .unwrap_or_default()
+    .to_string()
+}
+
+/// Whether `line` is an attribute, annotation, decorator or compiler
+/// directive, which may sit between a declaration and its documentation
+fn is_attribute(line: &str, style: DocStyle) -> bool {
+    match style {
+        DocStyle::Rust => line.starts_with("#["),
+        DocStyle::Go => line.starts_with("//go:") || line.starts_with("//nolint"),
+        DocStyle::CSharp => line.starts_with('['),
+        DocStyle::JsDoc | DocStyle::Python => line.starts_with('@'),
+    }
+}
+
+/// The docstring opening the body of the `def` or `class` on line `at`
+fn docstring(lines: &[&str], at: usize, end_line: Option<usize>) -> Option<String> {
+    let end = end_line.map_or(lines.len(), |e| e.min(lines.len()));
+    let header = (at..end).find(|&i| code(lines[i]).ends_with(':'))?;
+    let body = (header + 1..end)
+        .map(|i| lines[i].trim())
+        .find(|l| !l.is_empty() && !l.starts_with('#'))?;
+    string_literal(body)
+}
+
+/// A string literal on the first code line after line `at`
+fn string_after(lines: &[&str], at: usize) -> Option<String> {
+    let next = lines.get(at + 1)?.trim();
+    string_literal(next)
+}
+
+/// The first line of the string literal `line` starts with
+fn string_literal(line: &str) -> Option<String> {
+    let unprefixed = line.trim_start_matches(['r', 'R', 'u', 'U', 'b', 'B', 'f', 'F']);
+    if unprefixed.len() + 2 < line.len() {
+        return None;
+    }
+    let quote = ["\"\"\"", "'''", "\"", "'"]
+        .into_iter()
+        .find(|q| unprefixed.starts_with(q))?;
+    let text = &unprefixed[quote.len()..];
+    Some(
+        text.split(quote)
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    )
+}
+
+/// `line` without its trailing comment and whitespace
+fn code(line: &str) -> &str {
+    line.split(" #").next().unwrap_or_default().trim_end()
+}
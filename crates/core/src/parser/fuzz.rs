@@ -0,0 +1,239 @@
+//! Structured mutation of parser inputs
+//!
+//! Random bytes mostly exercise a grammar's error recovery; the parser bugs
+//! users hit sit closer to valid code. [`mutate`] starts from a valid
+//! source — a [`seeds`] corpus entry — and edits it the way real code goes
+//! wrong: lines deleted, duplicated or swapped, brackets and keywords
+//! inserted, the file cut short, identifiers made Unicode, enormous string
+//! literals and deep nesting added.
+//!
+//! [`check`] parses one input and reports what a parser must never do
+//! whatever its input: leave a graph that fails [`CodeGraph::validate`].
+//! Panics and non-termination are the caller's to catch, as only a thread
+//! of its own can. The `test_parser_fuzz` test drives both for a time
+//! budget; the cargo-fuzz targets under `crates/core/fuzz` call [`check`]
+//! on the fuzzer's inputs.
+
+use super::conformance::{self, Rng};
+use super::LanguageParser;
+use crate::graph::{CodeGraph, Violation};
+use std::path::{Path, PathBuf};
+
+/// Raw string literals longer than this in a parser's test file are seeds
+const MIN_SEED_LEN: usize = 16;
+
+/// Conformance fixtures per parser in the corpus
+const CONFORMANCE_SEEDS: u64 = 4;
+
+/// Fragments [`mutate`] inserts
+const TOKENS: &[&str] = &[
+    "{",
+    "}",
+    "(",
+    ")",
+    "[",
+    "]",
+    "<",
+    ">",
+    "\"",
+    "'",
+    "`",
+    "\"\"\"",
+    "/*",
+    "*/",
+    "//",
+    "#",
+    "@",
+    ":",
+    ";",
+    ",",
+    ".",
+    "=>",
+    "->",
+    "::",
+    "\\",
+    "\n",
+    "\r\n",
+    "\t",
+    "class ",
+    "def ",
+    "fn ",
+    "func ",
+    "fun ",
+    "function ",
+    "import ",
+    "use ",
+    "end",
+    "module ",
+    "struct ",
+    "impl ",
+    "interface ",
+    "return ",
+    "=",
+    "...",
+    "&",
+    "*",
+    "$",
+    "?",
+    "数据",
+    "ñ",
+    "\u{200B}",
+    "\u{FEFF}",
+    "𝔘𝔫𝔦",
+    "\u{0}",
+];
+
+/// Valid sources for `parser`: the raw string literals of its test file
+/// (`crates/core/tests/test_<language>_parser.rs`), the files of its
+/// languages under `tests/fixtures`, and conformance fixtures
+pub fn seeds(repo_root: &Path, parser: &dyn LanguageParser) -> Vec<String> {
+    let language = parser.language_name();
+    let mut seeds = Vec::new();
+
+    let stem = language.split('/').next().unwrap_or(language);
+    let tests = repo_root
+        .join("crates/core/tests")
+        .join(format!("test_{}_parser.rs", stem));
+    if let Ok(content) = std::fs::read_to_string(tests) {
+        seeds.extend(raw_strings(&content).filter(|s| s.len() >= MIN_SEED_LEN));
+    }
+
+    let fixtures = repo_root.join("tests/fixtures");
+    let files =
+        crate::discovery::discover_files_extended(&fixtures, parser.file_extensions(), &[], &[])
+            .unwrap_or_default();
+    seeds.extend(files.iter().filter_map(|f| std::fs::read_to_string(f).ok()));
+
+    seeds.extend(
+        (0..CONFORMANCE_SEEDS)
+            .filter_map(|seed| conformance::generate(language, seed))
+            .map(|fixture| fixture.source),
+    );
+    seeds
+}
+
+/// The contents of the `r#"..."#` literals in Rust source
+fn raw_strings(content: &str) -> impl Iterator<Item = String> + '_ {
+    content
+        .split("r#\"")
+        .skip(1)
+        .filter_map(|rest| rest.split_once("\"#").map(|(s, _)| s.to_string()))
+}
+
+/// File name for parsing an input with `parser`: its first extension
+pub fn file_name(parser: &dyn LanguageParser) -> PathBuf {
+    let extension = parser.file_extensions().first().copied().unwrap_or("");
+    PathBuf::from(format!("fuzz{}", extension))
+}
+
+/// `source` with one to four mutations applied
+pub fn mutate(source: &str, rng: &mut Rng) -> String {
+    let mut source = source.to_string();
+    for _ in 0..rng.range(1, 4) {
+        source = mutate_once(&source, rng);
+    }
+    source
+}
+
+fn mutate_once(source: &str, rng: &mut Rng) -> String {
+    let mut lines: Vec<&str> = source.lines().collect();
+    if lines.is_empty() {
+        return rng.pick(TOKENS).to_string();
+    }
+    let a = rng.range(0, lines.len() - 1);
+    let b = rng.range(a, (a + 8).min(lines.len() - 1));
+    match rng.range(0, 8) {
+        // Delete lines
+        0 => {
+            lines.drain(a..=b);
+            lines.join("\n")
+        }
+        // Duplicate lines
+        1 => {
+            let copy: Vec<&str> = lines[a..=b].to_vec();
+            lines.splice(b + 1..b + 1, copy);
+            lines.join("\n")
+        }
+        // Swap two lines
+        2 => {
+            let other = rng.range(0, lines.len() - 1);
+            lines.swap(a, other);
+            lines.join("\n")
+        }
+        // Cut the file short
+        3 => source[..boundary(source, rng)].to_string(),
+        // Make an identifier Unicode
+        4 => {
+            let line = lines[a];
+            let replaced = match line
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .find(|w| w.len() > 2)
+            {
+                Some(word) => line.replacen(word, &format!("{}ñ数据", word), 1),
+                None => line.to_string(),
+            };
+            lines[a] = &replaced;
+            lines.join("\n")
+        }
+        // An enormous string literal
+        5 => {
+            let literal = format!("x = \"{}\"", "a".repeat(rng.range(10_000, 200_000)));
+            lines.insert(a, &literal);
+            lines.join("\n")
+        }
+        // Deep nesting around lines
+        6 => {
+            let depth = rng.range(16, 256);
+            let (open, close) = *rng.pick(&[("{", "}"), ("(", ")"), ("[", "]"), ("if x {", "}")]);
+            let mut nested = open.repeat(depth);
+            nested.push('\n');
+            nested.push_str(&lines[a..=b].join("\n"));
+            nested.push('\n');
+            nested.push_str(&close.repeat(depth));
+            lines.splice(a..=b, [nested.as_str()]);
+            lines.join("\n")
+        }
+        // Insert a token
+        _ => {
+            let at = boundary(source, rng);
+            let token = rng.pick(TOKENS).repeat(rng.range(1, 3));
+            format!("{}{}{}", &source[..at], token, &source[at..])
+        }
+    }
+}
+
+/// A random char boundary of `source`
+fn boundary(source: &str, rng: &mut Rng) -> usize {
+    let mut at = rng.range(0, source.len());
+    while !source.is_char_boundary(at) {
+        at -= 1;
+    }
+    at
+}
+
+/// Parse `source` with `parser` and check the graph it leaves.
+///
+/// A parse error is a fine outcome; a graph failing
+/// [`CodeGraph::validate`] is not. Without `containment`, nodes outside
+/// their file's `Contains` tree are let through — for parsers the
+/// conformance baseline still lists as leaving them.
+pub fn check(parser: &dyn LanguageParser, source: &str, containment: bool) -> Result<(), String> {
+    let path = file_name(parser);
+    let mut graph = CodeGraph::new(PathBuf::from("/fuzz"));
+    if parser.parse_source(source, &path, &mut graph).is_err() {
+        return Ok(());
+    }
+    let Err(violations) = graph.validate() else {
+        return Ok(());
+    };
+    let violations: Vec<String> = violations
+        .iter()
+        .filter(|v| containment || !matches!(v, Violation::Uncontained { .. }))
+        .map(|v| v.to_string())
+        .collect();
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations.join("\n"))
+    }
+}
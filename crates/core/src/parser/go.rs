@@ -1,8 +1,9 @@
 //! Go language parser using Tree-sitter
 
+use super::docs::{self, DocStyle};
 use super::{
-    build_function_nodes_map, build_imports_map, collect_import_state, resolve_import_call,
-    LanguageParser, ParseError, ParseState, UnresolvedCall,
+    build_function_nodes_map, build_imports_map, collect_import_state, go_build,
+    resolve_import_call, LanguageParser, ParseError, ParseState, UnresolvedCall,
 };
 use crate::graph::{
     CodeGraph, Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeId, NodeKind, Parameter,
@@ -75,6 +76,7 @@ impl GoParser {
             0,
             NodeData::File {
                 language: "go".to_string(),
+                code_kind: crate::code_kind::classify(file_path, graph.root_path(), source),
             },
         );
         let file_node_id = graph.add_node(file_node);
@@ -255,6 +257,11 @@ impl GoParser {
         // Second pass: extract function calls to build call graph
         self.extract_calls(&root_node, source, graph, &function_nodes);
 
+        // Record build constraints so platform variants can be told apart
+        if let Some(expr) = go_build::file_constraint(file_path, source) {
+            go_build::tag_nodes(graph, file_path, &expr);
+        }
+
         node_ids
     }
 
@@ -1047,6 +1054,10 @@ impl LanguageParser for GoParser {
         &[".go"]
     }
 
+    fn doc_style(&self) -> Option<DocStyle> {
+        Some(DocStyle::Go)
+    }
+
     fn parse_file(
         &self,
         file_path: &Path,
@@ -1063,17 +1074,20 @@ impl LanguageParser for GoParser {
         graph: &mut CodeGraph,
     ) -> Result<Vec<NodeId>, ParseError> {
         let tree = self.parse_tree(source)?;
-        Ok(self.extract_nodes(&tree, source, file_path, graph))
+        let ids = self.extract_nodes(&tree, source, file_path, graph);
+        docs::record(graph, file_path, source, DocStyle::Go);
+        Ok(ids)
     }
 
-    fn parse_file_with_state(
+    fn parse_source_with_state(
         &self,
+        source: &str,
         file_path: &Path,
         graph: &mut CodeGraph,
     ) -> Result<(Vec<NodeId>, ParseState), ParseError> {
-        let source = std::fs::read_to_string(file_path)?;
-        let tree = self.parse_tree(&source)?;
-        let ids = self.extract_nodes(&tree, &source, file_path, graph);
+        let tree = self.parse_tree(source)?;
+        let ids = self.extract_nodes(&tree, source, file_path, graph);
+        docs::record(graph, file_path, source, DocStyle::Go);
 
         let mut state = collect_import_state(graph, file_path);
 
@@ -1082,7 +1096,7 @@ impl LanguageParser for GoParser {
             let function_nodes = build_function_nodes_map(graph, file_path);
             state.unresolved_calls = self.collect_cross_file_calls(
                 &tree,
-                &source,
+                source,
                 file_path,
                 &function_nodes,
                 &imports_map,
@@ -0,0 +1,581 @@
+//! Go build constraints: `//go:build` expressions and `_GOOS_GOARCH.go` suffixes
+//!
+//! The Go parser records a file's effective constraint as a `go:build <expr>`
+//! decorator on the File node and on every node the file contains. Platform
+//! variants of the same symbol (e.g. `Spawn` in `proc_linux.go` and
+//! `proc_windows.go`) can then be recognised as *alternatives* rather than
+//! duplicates by the cross-file resolver and the graph analyzers.
+
+use crate::finding::{Finding, Severity};
+use crate::graph::{CodeGraph, Node, NodeId, NodeKind};
+use crate::i18n::Msg;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Decorator prefix used to attach a build constraint to graph nodes
+pub const BUILD_DECORATOR_PREFIX: &str = "go:build ";
+
+/// Known GOOS values (from `go tool dist list`)
+const KNOWN_OS: &[&str] = &[
+    "aix",
+    "android",
+    "darwin",
+    "dragonfly",
+    "freebsd",
+    "hurd",
+    "illumos",
+    "ios",
+    "js",
+    "linux",
+    "nacl",
+    "netbsd",
+    "openbsd",
+    "plan9",
+    "solaris",
+    "wasip1",
+    "windows",
+    "zos",
+];
+
+/// Known GOARCH values
+const KNOWN_ARCH: &[&str] = &[
+    "386",
+    "amd64",
+    "amd64p32",
+    "arm",
+    "armbe",
+    "arm64",
+    "arm64be",
+    "loong64",
+    "mips",
+    "mipsle",
+    "mips64",
+    "mips64le",
+    "mips64p32",
+    "mips64p32le",
+    "ppc",
+    "ppc64",
+    "ppc64le",
+    "riscv",
+    "riscv64",
+    "s390",
+    "s390x",
+    "sparc",
+    "sparc64",
+    "wasm",
+];
+
+/// GOOS values satisfying the `unix` build tag
+const UNIX_OS: &[&str] = &[
+    "aix",
+    "android",
+    "darwin",
+    "dragonfly",
+    "freebsd",
+    "hurd",
+    "illumos",
+    "ios",
+    "linux",
+    "netbsd",
+    "openbsd",
+    "solaris",
+];
+
+/// A target platform, e.g. `linux/amd64`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    pub os: String,
+    pub arch: String,
+}
+
+impl Platform {
+    /// Parse `"os/arch"`. Returns `None` for malformed or unknown values.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (os, arch) = s.trim().split_once('/')?;
+        if !KNOWN_OS.contains(&os) || !KNOWN_ARCH.contains(&arch) {
+            return None;
+        }
+        Some(Self {
+            os: os.to_string(),
+            arch: arch.to_string(),
+        })
+    }
+
+    /// Evaluate a single build tag on this platform.
+    ///
+    /// Returns `None` for tags that don't describe the platform (e.g. `cgo`,
+    /// `integration`) — those are unknown at analysis time.
+    fn tag(&self, tag: &str) -> Option<bool> {
+        if KNOWN_OS.contains(&tag) {
+            // GOOS implications mirror the go tool: android ⇒ linux, ios ⇒ darwin,
+            // illumos ⇒ solaris.
+            let implied = match self.os.as_str() {
+                "android" => Some("linux"),
+                "ios" => Some("darwin"),
+                "illumos" => Some("solaris"),
+                _ => None,
+            };
+            return Some(self.os == tag || implied == Some(tag));
+        }
+        if KNOWN_ARCH.contains(&tag) {
+            return Some(self.arch == tag);
+        }
+        if tag == "unix" {
+            return Some(UNIX_OS.contains(&self.os.as_str()));
+        }
+        None
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.os, self.arch)
+    }
+}
+
+/// A parsed build constraint expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildExpr {
+    Tag(String),
+    Not(Box<BuildExpr>),
+    And(Box<BuildExpr>, Box<BuildExpr>),
+    Or(Box<BuildExpr>, Box<BuildExpr>),
+}
+
+impl BuildExpr {
+    /// Parse a `//go:build` expression (without the `//go:build` prefix)
+    pub fn parse(s: &str) -> Option<Self> {
+        let tokens = tokenize(s)?;
+        let mut pos = 0;
+        let expr = parse_or(&tokens, &mut pos)?;
+        if pos != tokens.len() {
+            return None;
+        }
+        Some(expr)
+    }
+
+    /// Three-valued evaluation: `None` when the result depends on tags that
+    /// don't describe the platform.
+    fn eval(&self, platform: &Platform) -> Option<bool> {
+        match self {
+            BuildExpr::Tag(t) => platform.tag(t),
+            BuildExpr::Not(e) => e.eval(platform).map(|b| !b),
+            BuildExpr::And(a, b) => match (a.eval(platform), b.eval(platform)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            BuildExpr::Or(a, b) => match (a.eval(platform), b.eval(platform)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+        }
+    }
+
+    /// Whether a file with this constraint may be compiled for `platform`.
+    /// Unknown (non-platform) tags are assumed satisfiable.
+    pub fn matches(&self, platform: &Platform) -> bool {
+        self.eval(platform).unwrap_or(true)
+    }
+
+    /// True if no known platform satisfies both constraints
+    pub fn is_disjoint(&self, other: &BuildExpr) -> bool {
+        !all_platforms().any(|p| self.matches(&p) && other.matches(&p))
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            BuildExpr::Or(..) => 1,
+            BuildExpr::And(..) => 2,
+            BuildExpr::Not(_) | BuildExpr::Tag(_) => 3,
+        }
+    }
+
+    fn fmt_child(&self, child: &BuildExpr, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if child.precedence() < self.precedence() {
+            write!(f, "({})", child)
+        } else {
+            write!(f, "{}", child)
+        }
+    }
+}
+
+impl fmt::Display for BuildExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildExpr::Tag(t) => write!(f, "{}", t),
+            BuildExpr::Not(e) => {
+                write!(f, "!")?;
+                self.fmt_child(e, f)
+            }
+            BuildExpr::And(a, b) => {
+                self.fmt_child(a, f)?;
+                write!(f, " && ")?;
+                self.fmt_child(b, f)
+            }
+            BuildExpr::Or(a, b) => {
+                self.fmt_child(a, f)?;
+                write!(f, " || ")?;
+                self.fmt_child(b, f)
+            }
+        }
+    }
+}
+
+fn all_platforms() -> impl Iterator<Item = Platform> {
+    KNOWN_OS.iter().flat_map(|os| {
+        KNOWN_ARCH.iter().map(move |arch| Platform {
+            os: os.to_string(),
+            arch: arch.to_string(),
+        })
+    })
+}
+
+// ── Expression parser ────────────────────────────────────────────
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Not,
+    And,
+    Or,
+    LParen,
+    RParen,
+}
+
+fn tokenize(s: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' | '\t' => {
+                chars.next();
+            }
+            '!' => {
+                chars.next();
+                tokens.push(Token::Not);
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            '&' | '|' => {
+                chars.next();
+                if chars.next() != Some(c) {
+                    return None;
+                }
+                tokens.push(if c == '&' { Token::And } else { Token::Or });
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' => {
+                let mut ident = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' || c == '.' {
+                        ident.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            _ => return None,
+        }
+    }
+    Some(tokens)
+}
+
+fn parse_or(tokens: &[Token], pos: &mut usize) -> Option<BuildExpr> {
+    let mut left = parse_and(tokens, pos)?;
+    while tokens.get(*pos) == Some(&Token::Or) {
+        *pos += 1;
+        let right = parse_and(tokens, pos)?;
+        left = BuildExpr::Or(Box::new(left), Box::new(right));
+    }
+    Some(left)
+}
+
+fn parse_and(tokens: &[Token], pos: &mut usize) -> Option<BuildExpr> {
+    let mut left = parse_unary(tokens, pos)?;
+    while tokens.get(*pos) == Some(&Token::And) {
+        *pos += 1;
+        let right = parse_unary(tokens, pos)?;
+        left = BuildExpr::And(Box::new(left), Box::new(right));
+    }
+    Some(left)
+}
+
+fn parse_unary(tokens: &[Token], pos: &mut usize) -> Option<BuildExpr> {
+    match tokens.get(*pos)? {
+        Token::Not => {
+            *pos += 1;
+            Some(BuildExpr::Not(Box::new(parse_unary(tokens, pos)?)))
+        }
+        Token::LParen => {
+            *pos += 1;
+            let inner = parse_or(tokens, pos)?;
+            if tokens.get(*pos) != Some(&Token::RParen) {
+                return None;
+            }
+            *pos += 1;
+            Some(inner)
+        }
+        Token::Ident(name) => {
+            *pos += 1;
+            Some(BuildExpr::Tag(name.clone()))
+        }
+        _ => None,
+    }
+}
+
+// ── File-level constraints ───────────────────────────────────────
+
+/// Constraint implied by a `_GOOS`, `_GOARCH`, or `_GOOS_GOARCH` filename suffix
+pub fn filename_constraint(file_path: &Path) -> Option<BuildExpr> {
+    let stem = file_path.file_name()?.to_str()?.strip_suffix(".go")?;
+    let stem = stem.strip_suffix("_test").unwrap_or(stem);
+    let parts: Vec<&str> = stem.split('_').collect();
+    let n = parts.len();
+    if n < 2 {
+        return None;
+    }
+
+    let last = parts[n - 1];
+    if n >= 3 && KNOWN_OS.contains(&parts[n - 2]) && KNOWN_ARCH.contains(&last) {
+        return Some(BuildExpr::And(
+            Box::new(BuildExpr::Tag(parts[n - 2].to_string())),
+            Box::new(BuildExpr::Tag(last.to_string())),
+        ));
+    }
+    if KNOWN_OS.contains(&last) || KNOWN_ARCH.contains(&last) {
+        return Some(BuildExpr::Tag(last.to_string()));
+    }
+    None
+}
+
+/// `//go:build` directive from the file header (before the `package` clause)
+pub fn directive_constraint(source: &str) -> Option<BuildExpr> {
+    for line in source.lines() {
+        let t = line.trim();
+        if let Some(expr) = t.strip_prefix("//go:build") {
+            return BuildExpr::parse(expr.trim());
+        }
+        if t.is_empty() || t.starts_with("//") {
+            continue;
+        }
+        if t.starts_with("/*") {
+            continue;
+        }
+        // First non-comment line (normally `package`) ends the header
+        break;
+    }
+    None
+}
+
+/// Effective constraint for a Go file: filename suffix AND `//go:build`
+pub fn file_constraint(file_path: &Path, source: &str) -> Option<BuildExpr> {
+    match (filename_constraint(file_path), directive_constraint(source)) {
+        (Some(a), Some(b)) if a == b => Some(a),
+        (Some(a), Some(b)) => Some(BuildExpr::And(Box::new(a), Box::new(b))),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Tag every node belonging to `file_path` with the file's build constraint
+pub(super) fn tag_nodes(graph: &mut CodeGraph, file_path: &Path, expr: &BuildExpr) {
+    let decorator = format!("{}{}", BUILD_DECORATOR_PREFIX, expr);
+    let ids: Vec<NodeId> = graph
+        .nodes()
+        .filter(|(_, n)| n.file_path() == file_path)
+        .map(|(id, _)| id)
+        .collect();
+    for id in ids {
+        if let Some(node) = graph.node_mut(id) {
+            let mut decorators = node.decorators().to_vec();
+            decorators.push(decorator.clone());
+            node.set_decorators(decorators);
+        }
+    }
+}
+
+/// Build constraint recorded on a node, if any
+pub fn constraint_of(node: &Node) -> Option<BuildExpr> {
+    node.decorators()
+        .iter()
+        .find_map(|d| d.strip_prefix(BUILD_DECORATOR_PREFIX))
+        .and_then(BuildExpr::parse)
+}
+
+/// Parse configured `[parser.go] platforms`, dropping malformed entries
+pub fn parse_platforms(values: &[String]) -> Vec<Platform> {
+    values.iter().filter_map(|v| Platform::parse(v)).collect()
+}
+
+/// Whether `node` is compiled for at least one configured platform.
+/// Always true when no platforms are configured or the node is unconstrained.
+pub fn is_active(node: &Node, platforms: &[Platform]) -> bool {
+    if platforms.is_empty() {
+        return true;
+    }
+    match constraint_of(node) {
+        Some(expr) => platforms.iter().any(|p| expr.matches(p)),
+        None => true,
+    }
+}
+
+// ── Platform variants ────────────────────────────────────────────
+
+/// A set of same-named symbols in one Go package whose build constraints are
+/// pairwise disjoint — conceptually one symbol with per-platform bodies.
+#[derive(Debug, Clone)]
+pub struct VariantGroup {
+    /// Member reported on behalf of the group (lowest file path)
+    pub representative: NodeId,
+    /// All members, including the representative
+    pub members: Vec<NodeId>,
+    /// Constraint of each member, rendered for notes (e.g. `["linux", "windows"]`)
+    pub platforms: Vec<String>,
+}
+
+impl VariantGroup {
+    /// Note appended to findings reported once on behalf of the group
+    pub fn note(&self) -> String {
+        format!("[platform variants: {}]", self.platforms.join(", "))
+    }
+}
+
+/// Index of platform variant groups, keyed by every member's NodeId
+#[derive(Debug, Default)]
+pub struct PlatformVariants {
+    groups: Vec<VariantGroup>,
+    by_member: HashMap<NodeId, usize>,
+}
+
+impl PlatformVariants {
+    /// Find all variant groups among the constrained nodes of `graph`
+    pub fn build(graph: &CodeGraph) -> Self {
+        let mut buckets: HashMap<(PathBuf, String, NodeKind), Vec<(NodeId, BuildExpr)>> =
+            HashMap::new();
+        for (id, node) in graph.nodes() {
+            if matches!(node.kind(), NodeKind::File | NodeKind::Import) {
+                continue;
+            }
+            let Some(expr) = constraint_of(node) else {
+                continue;
+            };
+            let dir = node
+                .file_path()
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default();
+            buckets
+                .entry((dir, node.name().to_string(), *node.kind()))
+                .or_default()
+                .push((id, expr));
+        }
+
+        let mut variants = PlatformVariants::default();
+        for (_, mut members) in buckets {
+            if members.len() < 2 {
+                continue;
+            }
+            let disjoint = members
+                .iter()
+                .enumerate()
+                .all(|(i, (_, a))| members[i + 1..].iter().all(|(_, b)| a.is_disjoint(b)));
+            if !disjoint {
+                continue;
+            }
+            members.sort_by_key(|(id, _)| {
+                graph
+                    .node(*id)
+                    .map(|n| (n.file_path().clone(), n.line()))
+                    .unwrap_or_default()
+            });
+            let idx = variants.groups.len();
+            for (id, _) in &members {
+                variants.by_member.insert(*id, idx);
+            }
+            variants.groups.push(VariantGroup {
+                representative: members[0].0,
+                platforms: members.iter().map(|(_, e)| e.to_string()).collect(),
+                members: members.into_iter().map(|(id, _)| id).collect(),
+            });
+        }
+        variants
+    }
+
+    /// Group containing `node_id`, if it is a platform variant
+    pub fn group_of(&self, node_id: NodeId) -> Option<&VariantGroup> {
+        self.by_member.get(&node_id).map(|&i| &self.groups[i])
+    }
+
+    /// True if there are no variant groups
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Collapse per-variant findings into one finding per `(group, kind)`.
+    ///
+    /// `findings` pairs each finding with the node it was raised on and a
+    /// check-specific `kind` (e.g. `"length"`). Findings on non-variant nodes
+    /// pass through unchanged. For variants, the most severe finding is kept
+    /// (the representative's on ties) and annotated with the platform list.
+    pub fn dedupe(&self, findings: Vec<(NodeId, &'static str, Finding)>) -> Vec<Finding> {
+        let mut out = Vec::new();
+        let mut best: HashMap<(usize, &'static str), (Rank, Finding)> = HashMap::new();
+        let mut order: Vec<(usize, &'static str)> = Vec::new();
+
+        for (node_id, kind, finding) in findings {
+            let Some(&group_idx) = self.by_member.get(&node_id) else {
+                out.push(finding);
+                continue;
+            };
+            let key = (group_idx, kind);
+            let rank = (
+                severity_rank(finding.severity),
+                self.groups[group_idx].representative == node_id,
+            );
+            match best.get(&key) {
+                Some((existing, _)) if *existing >= rank => {}
+                Some(_) => {
+                    best.insert(key, (rank, finding));
+                }
+                None => {
+                    order.push(key);
+                    best.insert(key, (rank, finding));
+                }
+            }
+        }
+
+        for key in order {
+            if let Some((_, mut finding)) = best.remove(&key) {
+                let group = &self.groups[key.0];
+                finding.message = format!("{} {}", finding.message, group.note());
+                finding.message_ref = finding.message_ref.take().map(|message| {
+                    Msg::new("platform-variants-note")
+                        .arg_msg("finding", message)
+                        .arg("platforms", group.platforms.join(", "))
+                });
+                out.push(finding);
+            }
+        }
+        out
+    }
+}
+
+/// Severity rank, then whether the finding is on the group's representative
+type Rank = (u8, bool);
+
+fn severity_rank(s: Severity) -> u8 {
+    match s {
+        Severity::Error => 2,
+        Severity::Warning => 1,
+        Severity::Info => 0,
+    }
+}
@@ -0,0 +1,116 @@
+//! Language families and tree-sitter grammars by file extension
+//!
+//! Analyzers that read syntax trees of their own (log arguments, flag
+//! checks, float comparisons, ...) look files up here rather than keeping
+//! extension tables, so `.mts` is TypeScript to every one of them. Each
+//! keeps the families it has rules for and ignores the rest.
+
+use std::path::Path;
+use tree_sitter::{Language, Parser, Tree};
+
+/// Language family: the languages sharing rules in the analyzers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Family {
+    Python,
+    /// JavaScript and TypeScript
+    Js,
+    Java,
+    Kotlin,
+    Groovy,
+    Scala,
+    Go,
+    Rust,
+    Ruby,
+    CSharp,
+    Php,
+    Swift,
+}
+
+impl Family {
+    /// The family of `path`, including those Revet has no grammar for
+    pub fn of(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        Some(match ext.as_str() {
+            "py" => Self::Python,
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => Self::Js,
+            "java" => Self::Java,
+            "kt" | "kts" => Self::Kotlin,
+            "groovy" => Self::Groovy,
+            "scala" => Self::Scala,
+            "go" => Self::Go,
+            "rs" => Self::Rust,
+            "rb" => Self::Ruby,
+            "cs" => Self::CSharp,
+            "php" => Self::Php,
+            "swift" => Self::Swift,
+            _ => return None,
+        })
+    }
+}
+
+/// The grammar a file is parsed with
+#[derive(Debug, Clone)]
+pub struct Grammar {
+    pub family: Family,
+    /// Code-fence language: `javascript`, `typescript` and `tsx` stay apart
+    pub name: &'static str,
+    pub language: Language,
+}
+
+impl Grammar {
+    /// Parse `source`; `None` when the grammar can't be loaded
+    pub fn parse(&self, source: &str) -> Option<Tree> {
+        let mut parser = Parser::new();
+        parser.set_language(&self.language).ok()?;
+        parser.parse(source, None)
+    }
+}
+
+/// The grammar of `path`, by extension
+pub fn language_for(path: &Path) -> Option<Grammar> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    let (family, name, language): (_, _, Language) = match ext.as_str() {
+        "py" => (
+            Family::Python,
+            "python",
+            tree_sitter_python::LANGUAGE.into(),
+        ),
+        "js" | "jsx" | "mjs" | "cjs" => (
+            Family::Js,
+            "javascript",
+            tree_sitter_javascript::LANGUAGE.into(),
+        ),
+        "ts" | "mts" | "cts" => (
+            Family::Js,
+            "typescript",
+            tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+        ),
+        "tsx" => (
+            Family::Js,
+            "tsx",
+            tree_sitter_typescript::LANGUAGE_TSX.into(),
+        ),
+        "java" => (Family::Java, "java", tree_sitter_java::LANGUAGE.into()),
+        "kt" | "kts" => (
+            Family::Kotlin,
+            "kotlin",
+            tree_sitter_kotlin_ng::LANGUAGE.into(),
+        ),
+        "go" => (Family::Go, "go", tree_sitter_go::LANGUAGE.into()),
+        "rs" => (Family::Rust, "rust", tree_sitter_rust::LANGUAGE.into()),
+        "rb" => (Family::Ruby, "ruby", tree_sitter_ruby::LANGUAGE.into()),
+        "cs" => (
+            Family::CSharp,
+            "csharp",
+            tree_sitter_c_sharp::LANGUAGE.into(),
+        ),
+        "php" => (Family::Php, "php", tree_sitter_php::LANGUAGE_PHP.into()),
+        "swift" => (Family::Swift, "swift", tree_sitter_swift::LANGUAGE.into()),
+        _ => return None,
+    };
+    Some(Grammar {
+        family,
+        name,
+        language,
+    })
+}
@@ -0,0 +1,283 @@
+//! A code graph kept up to date across edits
+//!
+//! [`IncrementalGraph`] holds the merged, resolved graph of a set of files
+//! with each file's content checksum and parse records. [`update`] re-parses
+//! only the files whose content changed: their nodes are removed
+//! ([`CodeGraph::remove_file`]), the files are parsed into graphs of their
+//! own and merged back, and [`CrossFileResolver::resolve_incremental`]
+//! re-links what touches them. The result is the graph a full
+//! [`ParserDispatcher::parse_files_parallel`] over the same files builds.
+//!
+//! Some edits reach further than the edited files; [`update`] rebuilds the
+//! whole graph for those instead:
+//!
+//! - files were added or removed: imports of unchanged files may resolve
+//!   elsewhere now
+//! - the graph has C# files: partial types and generated members are
+//!   linked across files
+//! - an edited Python file defines other functions or classes than before:
+//!   framework fixtures and dependencies are looked up by name across files
+//!
+//! [`update`]: IncrementalGraph::update
+//! [`CrossFileResolver::resolve_incremental`]: super::resolver::CrossFileResolver::resolve_incremental
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use super::{ParseState, ParserDispatcher, UnresolvedCall, UnresolvedImport};
+use crate::cache::GraphCache;
+use crate::cancel::{CancelToken, Cancelled};
+use crate::graph::{CodeGraph, NodeKind};
+
+/// What one file contributed to the graph
+struct FileEntry {
+    /// Content checksum when parsed; empty when the file couldn't be read
+    checksum: String,
+    /// Parse records, NodeIds pointing into the graph
+    state: ParseState,
+    error: Option<String>,
+}
+
+/// A file parsed on its own, not merged yet
+struct Parsed {
+    file: PathBuf,
+    checksum: String,
+    graph: CodeGraph,
+    state: ParseState,
+    error: Option<String>,
+}
+
+/// How [`IncrementalGraph::update`] brought the graph up to date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    /// No file changed
+    Unchanged,
+    /// This many changed files were re-parsed
+    Incremental(usize),
+    /// Every file was re-parsed
+    Rebuilt,
+}
+
+/// The resolved code graph of a set of files, updated per edit
+pub struct IncrementalGraph {
+    root: PathBuf,
+    graph: CodeGraph,
+    files: BTreeMap<PathBuf, FileEntry>,
+}
+
+impl IncrementalGraph {
+    /// Parse `files` into a new graph, as
+    /// [`ParserDispatcher::parse_files_parallel_cancellable`] does
+    pub fn build(
+        dispatcher: &ParserDispatcher,
+        files: &[PathBuf],
+        root: PathBuf,
+        token: &CancelToken,
+    ) -> Result<Self, Cancelled> {
+        let parsed = parse(dispatcher, &parseable(files), &root, token)?;
+        let mut graph = CodeGraph::new(root.clone());
+        let mut entries = BTreeMap::new();
+        for mut file in parsed {
+            let id_map = graph.merge(file.graph);
+            file.state.remap(&id_map);
+            entries.insert(file.file, entry(file.checksum, file.state, file.error));
+        }
+
+        let mut built = Self {
+            root,
+            graph,
+            files: entries,
+        };
+        let (imports, calls) = built.records();
+        dispatcher
+            .resolver(&built.root)
+            .resolve(&mut built.graph, imports, calls);
+        built.graph.assign_fingerprints();
+        Ok(built)
+    }
+
+    /// Bring the graph up to date with `files` (the full set, as
+    /// discovered now), re-parsing only those whose content changed.
+    ///
+    /// Stops with [`Cancelled`] while parsing, before the graph is touched;
+    /// a cancelled update leaves the previous graph in place.
+    pub fn update(
+        &mut self,
+        dispatcher: &ParserDispatcher,
+        files: &[PathBuf],
+        token: &CancelToken,
+    ) -> Result<Update, Cancelled> {
+        let files = parseable(files);
+        let same_files = files.len() == self.files.len()
+            && files.iter().all(|file| self.files.contains_key(file));
+        let has_csharp = self.files.keys().any(|file| is_ext(file, "cs"));
+        if !same_files || has_csharp {
+            return self.rebuild(dispatcher, &files, token);
+        }
+
+        let changed: Vec<PathBuf> = files
+            .par_iter()
+            .filter(|file| checksum(file) != self.files[*file].checksum)
+            .cloned()
+            .collect();
+        if changed.is_empty() {
+            return Ok(Update::Unchanged);
+        }
+
+        let parsed = parse(dispatcher, &changed, &self.root, token)?;
+        if parsed
+            .iter()
+            .any(|p| is_ext(&p.file, "py") && symbols(&p.graph) != self.symbols_of(&p.file))
+        {
+            return self.rebuild(dispatcher, &files, token);
+        }
+
+        // ── Swap the changed files' nodes (not cancellable from here) ──
+        for file in &changed {
+            let moved = self.graph.remove_file(file);
+            if !moved.is_empty() {
+                for entry in self.files.values_mut() {
+                    entry.state.remap(&moved);
+                }
+            }
+        }
+        for mut file in parsed {
+            let id_map = self.graph.merge(file.graph);
+            file.state.remap(&id_map);
+            self.files
+                .insert(file.file, entry(file.checksum, file.state, file.error));
+        }
+
+        let (imports, calls) = self.records();
+        let scope: HashSet<PathBuf> = changed.iter().cloned().collect();
+        dispatcher.resolver(&self.root).resolve_incremental(
+            &mut self.graph,
+            imports,
+            calls,
+            &scope,
+        );
+        self.graph.assign_fingerprints();
+        Ok(Update::Incremental(changed.len()))
+    }
+
+    /// The resolved graph
+    pub fn graph(&self) -> &CodeGraph {
+        &self.graph
+    }
+
+    /// Parse errors, in file order
+    pub fn errors(&self) -> Vec<String> {
+        self.files
+            .values()
+            .filter_map(|entry| entry.error.clone())
+            .collect()
+    }
+
+    fn rebuild(
+        &mut self,
+        dispatcher: &ParserDispatcher,
+        files: &[PathBuf],
+        token: &CancelToken,
+    ) -> Result<Update, Cancelled> {
+        *self = Self::build(dispatcher, files, self.root.clone(), token)?;
+        Ok(Update::Rebuilt)
+    }
+
+    /// Every file's unresolved imports and calls
+    fn records(&self) -> (Vec<UnresolvedImport>, Vec<UnresolvedCall>) {
+        let imports = self
+            .files
+            .values()
+            .flat_map(|entry| entry.state.unresolved_imports.iter().cloned())
+            .collect();
+        let calls = self
+            .files
+            .values()
+            .flat_map(|entry| entry.state.unresolved_calls.iter().cloned())
+            .collect();
+        (imports, calls)
+    }
+
+    /// The functions and classes the graph has for `file`
+    fn symbols_of(&self, file: &Path) -> HashSet<(String, NodeKind)> {
+        symbols_in(
+            self.graph
+                .find_nodes(file, None)
+                .into_iter()
+                .filter_map(|id| {
+                    let node = self.graph.node(id)?;
+                    Some((node.name().to_string(), *node.kind()))
+                }),
+        )
+    }
+}
+
+fn entry(checksum: String, state: ParseState, error: Option<String>) -> FileEntry {
+    FileEntry {
+        checksum,
+        state,
+        error,
+    }
+}
+
+/// `files` without dotfiles, which are never parsed
+fn parseable(files: &[PathBuf]) -> Vec<PathBuf> {
+    files
+        .iter()
+        .filter(|file| !crate::discovery::is_dotfile(file))
+        .cloned()
+        .collect()
+}
+
+/// Parse each of `files` into a graph of its own, in parallel
+fn parse(
+    dispatcher: &ParserDispatcher,
+    files: &[PathBuf],
+    root: &Path,
+    token: &CancelToken,
+) -> Result<Vec<Parsed>, Cancelled> {
+    let parsed: Option<Vec<Parsed>> = files
+        .par_iter()
+        .map(|file| {
+            if token.is_cancelled() {
+                return None;
+            }
+            // Checksum first: an edit racing the parse is picked up next time
+            let checksum = checksum(file);
+            let (graph, state, error) = dispatcher.parse_one(file, root);
+            Some(Parsed {
+                file: file.clone(),
+                checksum,
+                graph,
+                state,
+                error,
+            })
+        })
+        .collect();
+    parsed.ok_or(Cancelled)
+}
+
+fn checksum(file: &Path) -> String {
+    GraphCache::compute_file_checksum(file).unwrap_or_default()
+}
+
+fn is_ext(file: &Path, ext: &str) -> bool {
+    file.extension().and_then(|e| e.to_str()) == Some(ext)
+}
+
+/// The functions and classes of a file's graph
+fn symbols(graph: &CodeGraph) -> HashSet<(String, NodeKind)> {
+    symbols_in(
+        graph
+            .nodes()
+            .map(|(_, node)| (node.name().to_string(), *node.kind())),
+    )
+}
+
+fn symbols_in(nodes: impl Iterator<Item = (String, NodeKind)>) -> HashSet<(String, NodeKind)> {
+    nodes
+        .filter(|(_, kind)| matches!(kind, NodeKind::Function | NodeKind::Class))
+        .collect()
+}
@@ -1,5 +1,6 @@
 //! Java language parser using Tree-sitter
 
+use super::docs::{self, DocStyle};
 use super::{
     build_function_nodes_map, build_imports_map, collect_import_state, resolve_import_call,
     LanguageParser, ParseError, ParseState, UnresolvedCall,
@@ -83,6 +84,7 @@ impl JavaParser {
             0,
             NodeData::File {
                 language: "java".to_string(),
+                code_kind: crate::code_kind::classify(file_path, graph.root_path(), source),
             },
         );
         let file_node_id = graph.add_node(file_node);
@@ -193,6 +195,7 @@ impl JavaParser {
             },
         );
         class_node.set_end_line(node.end_position().row + 1);
+        class_node.set_decorators(extract_annotations(node, ctx.source));
 
         let class_id = ctx.graph.add_node(class_node);
         ctx.graph
@@ -249,6 +252,7 @@ impl JavaParser {
                             },
                         );
                         func_node.set_end_line(child.end_position().row + 1);
+                        func_node.set_decorators(extract_annotations(&child, ctx.source));
 
                         let func_id = ctx.graph.add_node(func_node);
                         ctx.function_nodes.insert(full_method_name, func_id);
@@ -266,6 +270,7 @@ impl JavaParser {
             NodeData::Interface { methods },
         );
         iface_node.set_end_line(node.end_position().row + 1);
+        iface_node.set_decorators(extract_annotations(node, ctx.source));
 
         let iface_id = ctx.graph.add_node(iface_node);
         ctx.graph
@@ -333,6 +338,7 @@ impl JavaParser {
             },
         );
         enum_node.set_end_line(node.end_position().row + 1);
+        enum_node.set_decorators(extract_annotations(node, ctx.source));
 
         let enum_id = ctx.graph.add_node(enum_node);
         ctx.graph
@@ -394,6 +400,7 @@ impl JavaParser {
             },
         );
         record_node.set_end_line(node.end_position().row + 1);
+        record_node.set_decorators(extract_annotations(node, ctx.source));
 
         let record_id = ctx.graph.add_node(record_node);
         ctx.graph
@@ -480,6 +487,7 @@ impl JavaParser {
             },
         );
         func_node.set_end_line(node.end_position().row + 1);
+        func_node.set_decorators(extract_annotations(node, ctx.source));
 
         let node_id = ctx.graph.add_node(func_node);
         Some((node_id, method_name))
@@ -507,6 +515,7 @@ impl JavaParser {
             },
         );
         func_node.set_end_line(node.end_position().row + 1);
+        func_node.set_decorators(extract_annotations(node, ctx.source));
 
         let node_id = ctx.graph.add_node(func_node);
         Some((node_id, ctor_name))
@@ -774,6 +783,10 @@ impl LanguageParser for JavaParser {
         &[".java"]
     }
 
+    fn doc_style(&self) -> Option<DocStyle> {
+        Some(DocStyle::JsDoc)
+    }
+
     fn parse_file(
         &self,
         file_path: &Path,
@@ -790,17 +803,20 @@ impl LanguageParser for JavaParser {
         graph: &mut CodeGraph,
     ) -> Result<Vec<NodeId>, ParseError> {
         let tree = self.parse_tree(source)?;
-        Ok(self.extract_nodes(&tree, source, file_path, graph))
+        let ids = self.extract_nodes(&tree, source, file_path, graph);
+        docs::record(graph, file_path, source, DocStyle::JsDoc);
+        Ok(ids)
     }
 
-    fn parse_file_with_state(
+    fn parse_source_with_state(
         &self,
+        source: &str,
         file_path: &Path,
         graph: &mut CodeGraph,
     ) -> Result<(Vec<NodeId>, ParseState), ParseError> {
-        let source = std::fs::read_to_string(file_path)?;
-        let tree = self.parse_tree(&source)?;
-        let ids = self.extract_nodes(&tree, &source, file_path, graph);
+        let tree = self.parse_tree(source)?;
+        let ids = self.extract_nodes(&tree, source, file_path, graph);
+        docs::record(graph, file_path, source, DocStyle::JsDoc);
 
         let mut state = collect_import_state(graph, file_path);
 
@@ -809,7 +825,7 @@ impl LanguageParser for JavaParser {
             let function_nodes = build_function_nodes_map(graph, file_path);
             state.unresolved_calls = self.collect_cross_file_calls(
                 &tree,
-                &source,
+                source,
                 file_path,
                 &function_nodes,
                 &imports_map,
@@ -953,6 +969,27 @@ fn has_modifiers(node: &tree_sitter::Node, source: &str, required: &[&str]) -> b
     false
 }
 
+/// Annotation names from a declaration's modifiers, without `@` or arguments
+/// (`@Transactional(readOnly = true)` → `Transactional`; qualified names are kept)
+pub(crate) fn extract_annotations(node: &tree_sitter::Node, source: &str) -> Vec<String> {
+    let mut annotations = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() != "modifiers" {
+            continue;
+        }
+        let mut mod_cursor = child.walk();
+        for modifier in child.children(&mut mod_cursor) {
+            if matches!(modifier.kind(), "marker_annotation" | "annotation") {
+                if let Some(name) = node_field_text(&modifier, "name", source) {
+                    annotations.push(name);
+                }
+            }
+        }
+    }
+    annotations
+}
+
 /// Build qualified method name by walking up to find the enclosing class
 fn find_enclosing_class_method(cursor: &TreeCursor, method_name: &str, source: &str) -> String {
     let mut temp_cursor = cursor.clone();
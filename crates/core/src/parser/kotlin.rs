@@ -71,6 +71,7 @@ impl KotlinParser {
             0,
             NodeData::File {
                 language: "kotlin".to_string(),
+                code_kind: crate::code_kind::classify(file_path, graph.root_path(), source),
             },
         );
         let file_node_id = graph.add_node(file_node);
@@ -450,12 +451,10 @@ impl KotlinParser {
                         }
                     }
                 }
-                "identifier" => {
-                    // After "as" keyword, this is the alias
-                    if has_as {
-                        if let Ok(text) = child.utf8_text(ctx.source.as_bytes()) {
-                            alias = Some(text.to_string());
-                        }
+                // After "as" keyword, this is the alias
+                "identifier" if has_as => {
+                    if let Ok(text) = child.utf8_text(ctx.source.as_bytes()) {
+                        alias = Some(text.to_string());
                     }
                 }
                 "*" => {
@@ -764,7 +763,7 @@ fn has_keyword(node: &tree_sitter::Node, source: &str, keyword: &str) -> bool {
 }
 
 /// Extract annotations from a node's modifiers child
-fn extract_annotations(node: &tree_sitter::Node, source: &str) -> Vec<String> {
+pub(crate) fn extract_annotations(node: &tree_sitter::Node, source: &str) -> Vec<String> {
     let mut decorators = Vec::new();
     let modifiers = match find_child_by_kind(node, "modifiers") {
         Some(m) => m,
@@ -1,19 +1,32 @@
 //! Language parsers for building the code graph from source files
 
 pub mod c;
+pub mod conformance;
 pub mod csharp;
+pub mod csharp_generated;
+pub mod csharp_partials;
+mod dev_deps;
+pub mod docs;
+pub mod fuzz;
 pub mod go;
+pub mod go_build;
+pub mod grammar;
+pub mod incremental;
 pub mod java;
 pub mod kotlin;
 pub mod php;
 pub mod python;
+pub mod python_frameworks;
 pub mod resolver;
 pub mod ruby;
 pub mod rust;
 pub mod swift;
 pub mod typescript;
 
-use crate::graph::{CodeGraph, EdgeKind, NodeData, NodeId, NodeKind};
+use crate::cancel::{CancelToken, Cancelled};
+use crate::graph::{
+    CodeGraph, EdgeKind, ImportKind, MergeMap, NodeData, NodeId, NodeKind, Violation,
+};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -49,6 +62,11 @@ pub struct UnresolvedImport {
     pub imported_names: Vec<String>,
     /// True for wildcard imports (`import *`, `from x import *`)
     pub is_wildcard: bool,
+    /// Classification the parser could tell from the statement alone
+    /// ([`ImportKind::TypeOnly`] or [`ImportKind::Runtime`]); the resolver
+    /// refines runtime imports to `test` and `dev`
+    #[serde(default)]
+    pub kind: ImportKind,
     /// Absolute path of the file that contains this import statement
     pub importing_file: PathBuf,
     /// NodeId of the File node for the importing file
@@ -62,7 +80,8 @@ pub struct UnresolvedCall {
     pub caller_node_id: NodeId,
     /// Name of the callee as it appears at the call site
     pub callee_name: String,
-    /// Module specifier this name was imported from
+    /// Module specifier this name was imported from; for C#, the type the
+    /// callee is a member of
     pub module_specifier: String,
     /// Source line of the call
     pub call_line: usize,
@@ -88,6 +107,31 @@ pub struct ParseState {
     pub source_file: Option<PathBuf>,
 }
 
+impl ParseState {
+    /// Rewrite every NodeId to its new ID after the owning graph was merged
+    /// into a larger one (see [`CodeGraph::merge`]).
+    pub fn remap(&mut self, id_map: &MergeMap) {
+        for imp in &mut self.unresolved_imports {
+            if let Some(&new_id) = id_map.get(&imp.import_node_id) {
+                imp.import_node_id = new_id;
+            }
+            if let Some(&new_id) = id_map.get(&imp.importing_file_node_id) {
+                imp.importing_file_node_id = new_id;
+            }
+        }
+        for call in &mut self.unresolved_calls {
+            if let Some(&new_id) = id_map.get(&call.caller_node_id) {
+                call.caller_node_id = new_id;
+            }
+        }
+        for id in self.exported_symbols.values_mut() {
+            if let Some(&new_id) = id_map.get(id) {
+                *id = new_id;
+            }
+        }
+    }
+}
+
 /// Trait for language-specific parsers
 ///
 /// Each language parser implements this trait to convert source code
@@ -99,6 +143,12 @@ pub trait LanguageParser: Send + Sync {
     /// Get file extensions this parser handles (e.g., [".py", ".pyi"])
     fn file_extensions(&self) -> &[&str];
 
+    /// The documentation comment convention this parser records on its
+    /// nodes (see [`docs::record`]), or `None` when it records none
+    fn doc_style(&self) -> Option<docs::DocStyle> {
+        None
+    }
+
     /// Parse a file and add its entities to the code graph
     ///
     /// Returns the IDs of top-level nodes created (e.g., functions, classes)
@@ -121,15 +171,30 @@ pub trait LanguageParser: Send + Sync {
     /// Parse a file and additionally return import/call metadata for
     /// cross-file resolution.
     ///
-    /// Parsers that implement cross-file tracking override this method.
-    /// The default falls back to [`parse_file`] and returns an empty
-    /// [`ParseState`] (no cross-file edges will be built for this file).
+    /// The default reads the file and delegates to
+    /// [`parse_source_with_state`], which parsers that implement cross-file
+    /// tracking override.
     fn parse_file_with_state(
         &self,
         file_path: &Path,
         graph: &mut CodeGraph,
     ) -> Result<(Vec<NodeId>, ParseState), ParseError> {
-        let ids = self.parse_file(file_path, graph)?;
+        let source = std::fs::read_to_string(file_path)?;
+        self.parse_source_with_state(&source, file_path, graph)
+    }
+
+    /// In-memory counterpart of [`parse_file_with_state`], used for unsaved
+    /// editor buffers.
+    ///
+    /// The default falls back to [`parse_source`] and returns an empty
+    /// [`ParseState`].
+    fn parse_source_with_state(
+        &self,
+        source: &str,
+        file_path: &Path,
+        graph: &mut CodeGraph,
+    ) -> Result<(Vec<NodeId>, ParseState), ParseError> {
+        let ids = self.parse_source(source, file_path, graph)?;
         Ok((ids, ParseState::default()))
     }
 }
@@ -137,6 +202,9 @@ pub trait LanguageParser: Send + Sync {
 /// Dispatcher that routes files to the appropriate language parser
 pub struct ParserDispatcher {
     parsers: Vec<Box<dyn LanguageParser>>,
+    go_platforms: Vec<go_build::Platform>,
+    python_frameworks: python_frameworks::Frameworks,
+    csharp_generators: csharp_generated::Generators,
 }
 
 impl ParserDispatcher {
@@ -156,16 +224,46 @@ impl ParserDispatcher {
                 Box::new(swift::SwiftParser::new()),
                 Box::new(typescript::TypeScriptParser::new()),
             ],
+            go_platforms: Vec::new(),
+            python_frameworks: python_frameworks::Frameworks::builtin(),
+            csharp_generators: csharp_generated::Generators::builtin(),
+        }
+    }
+
+    /// Create a dispatcher with default parsers and `[parser]` settings applied
+    pub fn new_with_config(config: &crate::config::RevetConfig) -> Self {
+        Self {
+            go_platforms: go_build::parse_platforms(&config.parser.go.platforms),
+            python_frameworks: python_frameworks::Frameworks::from_config(&config.parser.python),
+            csharp_generators: csharp_generated::Generators::from_config(&config.parser.csharp),
+            ..Self::new()
         }
     }
 
     /// Create a dispatcher with custom parsers
     pub fn with_parsers(parsers: Vec<Box<dyn LanguageParser>>) -> Self {
-        Self { parsers }
+        Self {
+            parsers,
+            go_platforms: Vec::new(),
+            python_frameworks: python_frameworks::Frameworks::builtin(),
+            csharp_generators: csharp_generated::Generators::builtin(),
+        }
+    }
+
+    /// The registered parsers
+    pub fn parsers(&self) -> impl Iterator<Item = &dyn LanguageParser> {
+        self.parsers.iter().map(|boxed| &**boxed)
     }
 
     /// Find a parser for the given file path based on extension
+    ///
+    /// Dotfiles (`.env.local`, `.eslintrc.js`) have no parser: discovery only
+    /// includes them for content analyzers, and their "extension" is not a
+    /// language.
     pub fn find_parser(&self, file_path: &Path) -> Option<&dyn LanguageParser> {
+        if crate::discovery::is_dotfile(file_path) {
+            return None;
+        }
         let extension = file_path.extension()?.to_str()?;
         let extension_with_dot = format!(".{}", extension);
 
@@ -207,37 +305,45 @@ impl ParserDispatcher {
         files: &[PathBuf],
         root: PathBuf,
     ) -> (CodeGraph, Vec<String>) {
+        match self.parse_parallel(files, root, None) {
+            Ok(parsed) => parsed,
+            Err(Cancelled) => unreachable!("no cancel token"),
+        }
+    }
+
+    /// [`parse_files_parallel`](Self::parse_files_parallel) that stops with
+    /// [`Cancelled`] when `token` is cancelled, checked before each file
+    pub fn parse_files_parallel_cancellable(
+        &self,
+        files: &[PathBuf],
+        root: PathBuf,
+        token: &CancelToken,
+    ) -> Result<(CodeGraph, Vec<String>), Cancelled> {
+        self.parse_parallel(files, root, Some(token))
+    }
+
+    fn parse_parallel(
+        &self,
+        files: &[PathBuf],
+        root: PathBuf,
+        token: Option<&CancelToken>,
+    ) -> Result<(CodeGraph, Vec<String>), Cancelled> {
+        let cancelled = || token.is_some_and(|t| t.is_cancelled());
+
         // ── Phase 1: parallel parse ───────────────────────────────────────────
         // Each file → its own CodeGraph + ParseState (no shared state, no locks)
-        let per_file: Vec<(CodeGraph, ParseState, Option<String>)> = files
+        // Dotfiles are content-analysis input only — not even a parse error
+        let per_file: Option<Vec<(CodeGraph, ParseState, Option<String>)>> = files
             .par_iter()
+            .filter(|file| !crate::discovery::is_dotfile(file))
             .map(|file| {
-                let mut local_graph = CodeGraph::new(root.clone());
-                match self.find_parser(file) {
-                    Some(parser) => match parser.parse_file_with_state(file, &mut local_graph) {
-                        Ok((_, state)) => (local_graph, state, None),
-                        Err(e) => (
-                            local_graph,
-                            ParseState::default(),
-                            Some(format!("{}: {}", file.display(), e)),
-                        ),
-                    },
-                    None => {
-                        let err = ParseError::UnsupportedLanguage(
-                            file.extension()
-                                .and_then(|e| e.to_str())
-                                .unwrap_or("unknown")
-                                .to_string(),
-                        );
-                        (
-                            local_graph,
-                            ParseState::default(),
-                            Some(format!("{}: {}", file.display(), err)),
-                        )
-                    }
+                if cancelled() {
+                    return None;
                 }
+                Some(self.parse_one(file, &root))
             })
             .collect();
+        let per_file = per_file.ok_or(Cancelled)?;
 
         // ── Phase 2: sequential merge + NodeId remapping ─────────────────────
         let mut graph = CodeGraph::new(root.clone());
@@ -249,19 +355,7 @@ impl ParserDispatcher {
             let id_map = graph.merge(local_graph);
 
             // Remap every NodeId in ParseState to its new ID in the merged graph
-            for imp in &mut state.unresolved_imports {
-                if let Some(&new_id) = id_map.get(&imp.import_node_id) {
-                    imp.import_node_id = new_id;
-                }
-                if let Some(&new_id) = id_map.get(&imp.importing_file_node_id) {
-                    imp.importing_file_node_id = new_id;
-                }
-            }
-            for call in &mut state.unresolved_calls {
-                if let Some(&new_id) = id_map.get(&call.caller_node_id) {
-                    call.caller_node_id = new_id;
-                }
-            }
+            state.remap(&id_map);
 
             all_imports.extend(state.unresolved_imports);
             all_calls.extend(state.unresolved_calls);
@@ -272,17 +366,62 @@ impl ParserDispatcher {
         }
 
         // ── Phase 3: cross-file resolution ───────────────────────────────────
-        let resolver = CrossFileResolver::new(&root);
-        resolver.resolve(&mut graph, all_imports, all_calls);
+        if cancelled() {
+            return Err(Cancelled);
+        }
+        self.resolver(&root)
+            .resolve(&mut graph, all_imports, all_calls);
+        graph.assign_fingerprints();
 
-        (graph, errors)
+        Ok((graph, errors))
+    }
+
+    /// Parse one file into a graph of its own; the error, if any, is
+    /// prefixed with the file's path
+    fn parse_one(&self, file: &Path, root: &Path) -> (CodeGraph, ParseState, Option<String>) {
+        let mut local_graph = CodeGraph::new(root.to_path_buf());
+        match self.find_parser(file) {
+            Some(parser) => match parser.parse_file_with_state(file, &mut local_graph) {
+                Ok((_, state)) => {
+                    debug_validate(&local_graph, file);
+                    (local_graph, state, None)
+                }
+                Err(e) => (
+                    local_graph,
+                    ParseState::default(),
+                    Some(format!("{}: {}", file.display(), e)),
+                ),
+            },
+            None => {
+                let err = ParseError::UnsupportedLanguage(
+                    file.extension()
+                        .and_then(|e| e.to_str())
+                        .unwrap_or("unknown")
+                        .to_string(),
+                );
+                (
+                    local_graph,
+                    ParseState::default(),
+                    Some(format!("{}: {}", file.display(), err)),
+                )
+            }
+        }
+    }
+
+    /// The cross-file resolver configured like this dispatcher
+    pub(crate) fn resolver<'r>(&self, root: &'r Path) -> CrossFileResolver<'r> {
+        CrossFileResolver::new(root)
+            .with_go_platforms(self.go_platforms.clone())
+            .with_python_frameworks(self.python_frameworks.clone())
+            .with_csharp_generators(self.csharp_generators.clone())
     }
 
     /// Incremental variant of [`parse_files_parallel`].
     ///
-    /// For each file whose content hash is present in `file_cache`, the cached
-    /// `(CodeGraph, ParseState)` fragment is reused instead of invoking
-    /// tree-sitter. Only changed or new files are actually re-parsed.
+    /// For each file whose path and content are present in `file_cache` (see
+    /// [`FileGraphCache::key`](crate::cache::FileGraphCache::key)), the
+    /// cached `(CodeGraph, ParseState)` fragment is reused instead of
+    /// invoking tree-sitter. Only changed or new files are actually re-parsed.
     ///
     /// Cross-file resolution (Phase 3) always runs because import/call edges
     /// span file boundaries and must reflect the current set of files.
@@ -294,13 +433,61 @@ impl ParserDispatcher {
         root: PathBuf,
         file_cache: &crate::cache::FileGraphCache,
     ) -> (CodeGraph, Vec<String>, usize, usize) {
+        let (mut graph, records, errors, cached_count, parsed_count) =
+            self.merge_incremental(files, &root, file_cache);
+
+        // ── Phase 3: cross-file resolution ───────────────────────────────────
+        self.resolver(&root).resolve(
+            &mut graph,
+            records.unresolved_imports,
+            records.unresolved_calls,
+        );
+        graph.assign_fingerprints();
+
+        (graph, errors, cached_count, parsed_count)
+    }
+
+    /// [`parse_files_incremental`](Self::parse_files_incremental), also
+    /// returning every file's unresolved imports and calls, NodeIds pointing
+    /// into the graph, for re-resolving after a file is swapped out.
+    ///
+    /// Returns `(merged_graph, records, parse_errors)`.
+    pub(crate) fn parse_files_incremental_with_records(
+        &self,
+        files: &[PathBuf],
+        root: PathBuf,
+        file_cache: &crate::cache::FileGraphCache,
+    ) -> (CodeGraph, ParseState, Vec<String>) {
+        let (mut graph, records, errors, _, _) = self.merge_incremental(files, &root, file_cache);
+
+        self.resolver(&root).resolve(
+            &mut graph,
+            records.unresolved_imports.clone(),
+            records.unresolved_calls.clone(),
+        );
+        graph.assign_fingerprints();
+
+        (graph, records, errors)
+    }
+
+    /// Phases 1 and 2 of [`parse_files_incremental`](Self::parse_files_incremental):
+    /// the merged, unresolved graph and the records to resolve it with.
+    fn merge_incremental(
+        &self,
+        files: &[PathBuf],
+        root: &Path,
+        file_cache: &crate::cache::FileGraphCache,
+    ) -> (CodeGraph, ParseState, Vec<String>, usize, usize) {
+        let root = root.to_path_buf();
         // ── Phase 1: parallel parse (cache-aware) ────────────────────────────
         let per_file: Vec<(CodeGraph, ParseState, Option<String>, bool)> = files
             .par_iter()
+            .filter(|file| !crate::discovery::is_dotfile(file))
             .map(|file| {
                 // Try cache first
-                if let Ok(hash) = crate::cache::GraphCache::compute_file_checksum(file) {
-                    if let Some((cached_graph, cached_state)) = file_cache.load(&hash) {
+                let key = crate::cache::FileGraphCache::key(&root, file);
+                if let Ok(key) = &key {
+                    if let Some((cached_graph, cached_state)) = file_cache.load(key, &root, file) {
                         return (cached_graph, cached_state, None, true);
                     }
                 }
@@ -310,10 +497,10 @@ impl ParserDispatcher {
                 match self.find_parser(file) {
                     Some(parser) => match parser.parse_file_with_state(file, &mut local_graph) {
                         Ok((_, state)) => {
+                            debug_validate(&local_graph, file);
                             // Persist for next run
-                            if let Ok(hash) = crate::cache::GraphCache::compute_file_checksum(file)
-                            {
-                                file_cache.save(&hash, &local_graph, &state);
+                            if let Ok(key) = &key {
+                                file_cache.save(key, &local_graph, &state);
                             }
                             (local_graph, state, None, false)
                         }
@@ -353,19 +540,7 @@ impl ParserDispatcher {
         for (local_graph, mut state, err, from_cache) in per_file {
             let id_map = graph.merge(local_graph);
 
-            for imp in &mut state.unresolved_imports {
-                if let Some(&new_id) = id_map.get(&imp.import_node_id) {
-                    imp.import_node_id = new_id;
-                }
-                if let Some(&new_id) = id_map.get(&imp.importing_file_node_id) {
-                    imp.importing_file_node_id = new_id;
-                }
-            }
-            for call in &mut state.unresolved_calls {
-                if let Some(&new_id) = id_map.get(&call.caller_node_id) {
-                    call.caller_node_id = new_id;
-                }
-            }
+            state.remap(&id_map);
 
             all_imports.extend(state.unresolved_imports);
             all_calls.extend(state.unresolved_calls);
@@ -381,11 +556,37 @@ impl ParserDispatcher {
             }
         }
 
-        // ── Phase 3: cross-file resolution ───────────────────────────────────
-        let resolver = CrossFileResolver::new(&root);
-        resolver.resolve(&mut graph, all_imports, all_calls);
+        let records = ParseState {
+            unresolved_imports: all_imports,
+            unresolved_calls: all_calls,
+            ..Default::default()
+        };
+        (graph, records, errors, cached_count, parsed_count)
+    }
 
-        (graph, errors, cached_count, parsed_count)
+    /// Record in `ledger` which of `files` were parsed into the graph and
+    /// which failed, given the errors returned by
+    /// [`parse_files_parallel`](Self::parse_files_parallel) or
+    /// [`parse_files_incremental`](Self::parse_files_incremental).
+    ///
+    /// Files without a parser are left alone; content analyzers may still
+    /// cover them.
+    pub fn record_coverage(
+        &self,
+        files: &[PathBuf],
+        errors: &[String],
+        ledger: &mut crate::coverage::CoverageLedger,
+    ) {
+        for file in files {
+            if self.find_parser(file).is_none() {
+                continue;
+            }
+            let prefix = format!("{}: ", file.display());
+            match errors.iter().find_map(|e| e.strip_prefix(&prefix)) {
+                Some(error) => ledger.parse_failed(file, error),
+                None => ledger.parsed(file),
+            }
+        }
     }
 
     /// Get all supported file extensions
@@ -397,6 +598,29 @@ impl ParserDispatcher {
     }
 }
 
+/// Debug builds check each file's graph as it is parsed: no edge or index
+/// entry may refer to a missing node. Containment is not asserted — the
+/// parser conformance baseline lists the parsers that still leave nodes
+/// outside their file's `Contains` tree.
+fn debug_validate(graph: &CodeGraph, file: &Path) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    if let Err(violations) = graph.validate() {
+        let broken: Vec<String> = violations
+            .iter()
+            .filter(|v| !matches!(v, Violation::Uncontained { .. }))
+            .map(|v| v.to_string())
+            .collect();
+        debug_assert!(
+            broken.is_empty(),
+            "{}: {}",
+            file.display(),
+            broken.join("; ")
+        );
+    }
+}
+
 impl Default for ParserDispatcher {
     fn default() -> Self {
         Self::new()
@@ -425,13 +649,13 @@ pub(super) fn collect_import_state(graph: &CodeGraph, file_path: &std::path::Pat
     };
 
     // Collect import target NodeIds first (avoid borrow overlap)
-    let import_ids: Vec<NodeId> = graph
+    let import_ids: Vec<(NodeId, ImportKind)> = graph
         .edges_from(fid)
         .filter(|(_, e)| matches!(e.kind(), EdgeKind::Imports))
-        .map(|(target, _)| target)
+        .map(|(target, e)| (target, e.import_kind()))
         .collect();
 
-    for import_id in import_ids {
+    for (import_id, kind) in import_ids {
         if let Some(node) = graph.node(import_id) {
             if let NodeData::Import {
                 module,
@@ -449,6 +673,7 @@ pub(super) fn collect_import_state(graph: &CodeGraph, file_path: &std::path::Pat
                         .cloned()
                         .collect(),
                     is_wildcard,
+                    kind,
                     importing_file: file_path.to_path_buf(),
                     importing_file_node_id: fid,
                 });
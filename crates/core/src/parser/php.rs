@@ -83,6 +83,7 @@ impl PhpParser {
             0,
             NodeData::File {
                 language: "php".to_string(),
+                code_kind: crate::code_kind::classify(file_path, graph.root_path(), source),
             },
         );
         let file_node_id = graph.add_node(file_node);
@@ -953,14 +954,14 @@ impl LanguageParser for PhpParser {
         Ok(self.extract_nodes(&tree, source, file_path, graph))
     }
 
-    fn parse_file_with_state(
+    fn parse_source_with_state(
         &self,
+        source: &str,
         file_path: &Path,
         graph: &mut CodeGraph,
     ) -> Result<(Vec<NodeId>, ParseState), ParseError> {
-        let source = std::fs::read_to_string(file_path)?;
-        let tree = self.parse_tree(&source)?;
-        let ids = self.extract_nodes(&tree, &source, file_path, graph);
+        let tree = self.parse_tree(source)?;
+        let ids = self.extract_nodes(&tree, source, file_path, graph);
 
         let mut state = collect_import_state(graph, file_path);
 
@@ -969,7 +970,7 @@ impl LanguageParser for PhpParser {
             let function_nodes = build_function_nodes_map(graph, file_path);
             state.unresolved_calls = self.collect_cross_file_calls(
                 &tree,
-                &source,
+                source,
                 file_path,
                 &function_nodes,
                 &imports_map,
@@ -1,11 +1,13 @@
 //! Python language parser using Tree-sitter
 
+use super::docs::{self, DocStyle};
 use super::{
     build_function_nodes_map, build_imports_map, collect_import_state, resolve_import_call,
     LanguageParser, ParseError, ParseState, UnresolvedCall,
 };
 use crate::graph::{
-    CodeGraph, Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeId, NodeKind, Parameter,
+    CodeGraph, Edge, EdgeKind, EdgeMetadata, ImportKind, Node, NodeData, NodeId, NodeKind,
+    Parameter,
 };
 use std::collections::HashMap;
 use std::path::Path;
@@ -75,6 +77,7 @@ impl PythonParser {
             0,
             NodeData::File {
                 language: "python".to_string(),
+                code_kind: crate::code_kind::classify(file_path, graph.root_path(), source),
             },
         );
         let file_node_id = graph.add_node(file_node);
@@ -105,6 +108,7 @@ impl PythonParser {
                                 graph,
                                 &mut function_nodes,
                             ) {
+                                set_decorators(graph, node_id, &child, source);
                                 graph.add_edge(
                                     file_node_id,
                                     node_id,
@@ -122,6 +126,7 @@ impl PythonParser {
                                 graph,
                                 &mut function_nodes,
                             ) {
+                                set_decorators(graph, node_id, &child, source);
                                 graph.add_edge(
                                     file_node_id,
                                     node_id,
@@ -146,6 +151,27 @@ impl PythonParser {
                         node_ids.push(node_id);
                     }
                 }
+                // `if TYPE_CHECKING:` imports exist only for the type checker
+                "if_statement" if is_type_checking(&child, source) => {
+                    let Some(body) = child.child_by_field_name("consequence") else {
+                        continue;
+                    };
+                    let mut body_cursor = body.walk();
+                    for stmt in body.children(&mut body_cursor) {
+                        if !matches!(stmt.kind(), "import_statement" | "import_from_statement") {
+                            continue;
+                        }
+                        if let Some(node_id) = self.extract_import(&stmt, source, file_path, graph)
+                        {
+                            graph.add_edge(
+                                file_node_id,
+                                node_id,
+                                Edge::import(ImportKind::TypeOnly),
+                            );
+                            node_ids.push(node_id);
+                        }
+                    }
+                }
                 _ => {}
             }
         }
@@ -236,6 +262,7 @@ impl PythonParser {
                                 graph,
                                 function_nodes,
                             ) {
+                                set_decorators(graph, nested_id, &child, source);
                                 graph.add_edge(
                                     parent_function_id,
                                     nested_id,
@@ -344,6 +371,7 @@ impl PythonParser {
                                     graph,
                                     function_nodes,
                                 ) {
+                                    set_decorators(graph, method_node_id, &child, source);
                                     methods.push(method_name.clone());
                                     let qualified_name = format!("{}.{}", class_name, method_name);
                                     function_nodes.insert(qualified_name, method_node_id);
@@ -735,6 +763,36 @@ impl PythonParser {
     }
 }
 
+/// Whether `node` is `if TYPE_CHECKING:` (or `typing.TYPE_CHECKING`)
+fn is_type_checking(node: &tree_sitter::Node, source: &str) -> bool {
+    node.child_by_field_name("condition")
+        .and_then(|c| c.utf8_text(source.as_bytes()).ok())
+        .is_some_and(|c| matches!(c.trim(), "TYPE_CHECKING" | "typing.TYPE_CHECKING"))
+}
+
+/// Record the decorators of a `decorated_definition` on `node_id`, without
+/// the `@` and with whitespace collapsed: `app.get("/users/{id}")`
+fn set_decorators(
+    graph: &mut CodeGraph,
+    node_id: NodeId,
+    decorated: &tree_sitter::Node,
+    source: &str,
+) {
+    let mut cursor = decorated.walk();
+    let decorators: Vec<String> = decorated
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "decorator")
+        .filter_map(|c| c.utf8_text(source.as_bytes()).ok())
+        .map(|text| {
+            let text = text.trim_start_matches('@');
+            text.split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .collect();
+    if let Some(node) = graph.node_mut(node_id) {
+        node.set_decorators(decorators);
+    }
+}
+
 impl LanguageParser for PythonParser {
     fn language_name(&self) -> &str {
         "python"
@@ -744,6 +802,10 @@ impl LanguageParser for PythonParser {
         &[".py", ".pyi"]
     }
 
+    fn doc_style(&self) -> Option<DocStyle> {
+        Some(DocStyle::Python)
+    }
+
     fn parse_file(
         &self,
         file_path: &Path,
@@ -760,18 +822,21 @@ impl LanguageParser for PythonParser {
         graph: &mut CodeGraph,
     ) -> Result<Vec<NodeId>, ParseError> {
         let tree = self.parse_tree(source)?;
-        Ok(self.extract_nodes(&tree, source, file_path, graph))
+        let ids = self.extract_nodes(&tree, source, file_path, graph);
+        docs::record(graph, file_path, source, DocStyle::Python);
+        Ok(ids)
     }
 
-    fn parse_file_with_state(
+    fn parse_source_with_state(
         &self,
+        source: &str,
         file_path: &Path,
         graph: &mut CodeGraph,
     ) -> Result<(Vec<NodeId>, ParseState), ParseError> {
         // Parse once, keep the tree so we can walk it for cross-file calls.
-        let source = std::fs::read_to_string(file_path)?;
-        let tree = self.parse_tree(&source)?;
-        let ids = self.extract_nodes(&tree, &source, file_path, graph);
+        let tree = self.parse_tree(source)?;
+        let ids = self.extract_nodes(&tree, source, file_path, graph);
+        docs::record(graph, file_path, source, DocStyle::Python);
 
         let mut state = collect_import_state(graph, file_path);
 
@@ -781,7 +846,7 @@ impl LanguageParser for PythonParser {
             let function_nodes = build_function_nodes_map(graph, file_path);
             state.unresolved_calls = self.collect_cross_file_calls(
                 &tree,
-                &source,
+                source,
                 file_path,
                 &function_nodes,
                 &imports_map,
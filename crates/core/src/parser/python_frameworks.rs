@@ -0,0 +1,455 @@
+//! Python frameworks: functions called by a framework and injected dependencies
+//!
+//! Flask and FastAPI routes, Celery tasks, click commands and pytest fixtures
+//! are invoked by their framework, never by the application, and FastAPI
+//! wires `Depends(get_db)` together without a call. After cross-file
+//! resolution, [`link`] applies the `[parser.python.frameworks]` table (on top
+//! of the built-in one) to the graph:
+//!
+//! - functions and classes carrying a framework decorator are tagged with an
+//!   `entry-point <framework>` decorator, which the unused-export check treats
+//!   as a root
+//! - a function gets a [`EdgeKind::References`] edge to every function named
+//!   by a dependency call in its decorators or parameter defaults
+//!   (`db: Session = Depends(get_db)`, `dependencies=[Depends(auth)]`)
+//! - tests and fixtures get a `References` edge with `Medium` confidence to
+//!   the fixtures their parameters name, looked up in the same file, the
+//!   same package directory, then the `conftest.py` of enclosing directories
+
+use crate::config::{PythonFrameworkConfig, PythonParserConfig};
+use crate::finding::Confidence;
+use crate::graph::{CodeGraph, Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeId, NodeKind};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Decorator prefix marking a function the named framework invokes
+pub const ENTRY_POINT_PREFIX: &str = "entry-point ";
+
+/// Built-in frameworks: name, registering decorators, dependency callables
+const BUILTIN: &[(&str, &[&str], &[&str])] = &[
+    (
+        "flask",
+        &[
+            "*.route",
+            "*.before_request",
+            "*.after_request",
+            "*.teardown_request",
+            "*.teardown_appcontext",
+            "*.errorhandler",
+            "*.context_processor",
+            "*.template_filter",
+        ],
+        &[],
+    ),
+    (
+        "fastapi",
+        &[
+            "*.get",
+            "*.post",
+            "*.put",
+            "*.patch",
+            "*.delete",
+            "*.head",
+            "*.options",
+            "*.api_route",
+            "*.websocket",
+            "*.on_event",
+            "*.middleware",
+            "*.exception_handler",
+        ],
+        &["Depends", "Security"],
+    ),
+    ("celery", &["*.task", "shared_task", "*.periodic_task"], &[]),
+    (
+        "click",
+        &["click.command", "click.group", "*.command", "*.group"],
+        &[],
+    ),
+    (
+        "pytest",
+        &[
+            "pytest.fixture",
+            "pytest_asyncio.fixture",
+            "fixture",
+            "pytest.hookimpl",
+        ],
+        &[],
+    ),
+];
+
+/// Decorators that declare a pytest fixture
+const FIXTURE_DECORATORS: &[&str] = &["pytest.fixture", "pytest_asyncio.fixture", "fixture"];
+
+/// Parameters pytest fills in itself, or that are not fixtures at all
+const NOT_FIXTURES: &[&str] = &["self", "cls", "request"];
+
+/// The effective framework table
+#[derive(Debug, Clone)]
+pub struct Frameworks {
+    frameworks: Vec<Framework>,
+    /// `Depends(name)`-style calls of every framework; `None` without any
+    dependency: Option<Regex>,
+}
+
+#[derive(Debug, Clone)]
+struct Framework {
+    name: String,
+    decorators: Vec<Regex>,
+}
+
+impl Frameworks {
+    /// The built-in table only
+    pub fn builtin() -> Self {
+        Self::from_config(&PythonParserConfig::default())
+    }
+
+    /// The built-in table with `[parser.python.frameworks]` applied: entries
+    /// with a built-in name replace it, others are added
+    pub fn from_config(config: &PythonParserConfig) -> Self {
+        let mut table: BTreeMap<String, PythonFrameworkConfig> = BUILTIN
+            .iter()
+            .map(|(name, decorators, dependencies)| {
+                let strings = |list: &[&str]| list.iter().map(|s| s.to_string()).collect();
+                (
+                    name.to_string(),
+                    PythonFrameworkConfig {
+                        decorators: strings(decorators),
+                        dependencies: strings(dependencies),
+                    },
+                )
+            })
+            .collect();
+        table.extend(config.frameworks.clone());
+
+        let frameworks = table
+            .iter()
+            .map(|(name, entry)| Framework {
+                name: name.clone(),
+                decorators: entry
+                    .decorators
+                    .iter()
+                    .filter_map(|pattern| glob(pattern))
+                    .collect(),
+            })
+            .collect();
+        let mut callables: Vec<String> = table
+            .values()
+            .flat_map(|entry| entry.dependencies.iter().map(|d| regex::escape(d)))
+            .collect();
+        callables.sort();
+        callables.dedup();
+        let dependency = (!callables.is_empty())
+            .then(|| {
+                Regex::new(&format!(
+                    r"(?:^|[^\w.])(?:\w+\.)*(?:{})\s*\(\s*(?:dependency\s*=\s*)?([A-Za-z_][\w.]*)\s*[,)]",
+                    callables.join("|")
+                ))
+                .ok()
+            })
+            .flatten();
+        Self {
+            frameworks,
+            dependency,
+        }
+    }
+
+    /// The framework a decorator (without `@`) registers its function with
+    pub fn registering(&self, decorator: &str) -> Option<&str> {
+        let callee = callee(decorator);
+        self.frameworks
+            .iter()
+            .find(|f| f.decorators.iter().any(|re| re.is_match(callee)))
+            .map(|f| f.name.as_str())
+    }
+
+    /// Names of the functions injected by dependency calls in `text`
+    fn dependencies<'t>(&self, text: &'t str) -> Vec<&'t str> {
+        let Some(re) = &self.dependency else {
+            return Vec::new();
+        };
+        re.captures_iter(text)
+            .filter_map(|c| c.get(1))
+            .map(|m| m.as_str())
+            .collect()
+    }
+}
+
+impl Default for Frameworks {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+/// The framework that invokes `node`, if it is an entry point
+pub fn entry_point(node: &Node) -> Option<&str> {
+    node.decorators()
+        .iter()
+        .find_map(|d| d.strip_prefix(ENTRY_POINT_PREFIX))
+}
+
+/// `*.task` → `^.*\.task$`
+fn glob(pattern: &str) -> Option<Regex> {
+    let body = pattern
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(".*");
+    Regex::new(&format!("^{}$", body)).ok()
+}
+
+/// `app.get("/users")` → `app.get`
+fn callee(decorator: &str) -> &str {
+    decorator.split('(').next().unwrap_or(decorator).trim()
+}
+
+fn is_python(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("py" | "pyi")
+    )
+}
+
+/// An import of one Python file, as recorded on its `Import` node
+struct Import<'g> {
+    module: &'g str,
+    names: &'g [String],
+    resolved: Option<&'g PathBuf>,
+}
+
+/// Lookups over the Python part of a graph
+struct Index<'g> {
+    root: &'g Path,
+    files: HashSet<&'g Path>,
+    symbols: HashMap<(&'g Path, &'g str), NodeId>,
+    imports: HashMap<&'g Path, Vec<Import<'g>>>,
+}
+
+impl<'g> Index<'g> {
+    fn build(graph: &'g CodeGraph, root: &'g Path) -> Self {
+        let mut index = Index {
+            root,
+            files: HashSet::new(),
+            symbols: HashMap::new(),
+            imports: HashMap::new(),
+        };
+        for (id, node) in graph.nodes() {
+            let path = node.file_path().as_path();
+            if !is_python(path) {
+                continue;
+            }
+            match node.data() {
+                NodeData::File { .. } => {
+                    index.files.insert(path);
+                }
+                NodeData::Import {
+                    module,
+                    imported_names,
+                    resolved_path,
+                } => index.imports.entry(path).or_default().push(Import {
+                    module,
+                    names: imported_names,
+                    resolved: resolved_path.as_ref(),
+                }),
+                _ => {
+                    index.symbols.entry((path, node.name())).or_insert(id);
+                }
+            }
+        }
+        index
+    }
+
+    /// The symbol `name` (`get_db` or `deps.get_db`) refers to in `file`
+    fn resolve(&self, file: &Path, name: &str) -> Option<NodeId> {
+        let imports = self.imports.get(file).map_or(&[][..], Vec::as_slice);
+        match name.rsplit_once('.') {
+            None => self.symbols.get(&(file, name)).copied().or_else(|| {
+                imports
+                    .iter()
+                    .filter(|i| i.names.iter().any(|n| n == name))
+                    .find_map(|i| {
+                        let target = match i.resolved {
+                            Some(path) => path.clone(),
+                            None => self.module_file(i.module, file)?,
+                        };
+                        self.symbols.get(&(target.as_path(), name)).copied()
+                    })
+            }),
+            Some((qualifier, name)) => imports.iter().find_map(|i| {
+                let module = if i.names.iter().any(|n| n == qualifier) {
+                    // `from app import deps` … `deps.get_db`
+                    let separator = if i.module.ends_with('.') { "" } else { "." };
+                    format!("{}{}{}", i.module, separator, qualifier)
+                } else if i.module == qualifier {
+                    // `import app.deps` … `app.deps.get_db`
+                    i.module.to_string()
+                } else {
+                    return None;
+                };
+                let target = self.module_file(&module, file)?;
+                self.symbols.get(&(target.as_path(), name)).copied()
+            }),
+        }
+    }
+
+    /// The file of a module imported by `importing_file`, relative
+    /// (`.deps`, `..core.db`) or from the repository root (`app.deps`)
+    fn module_file(&self, module: &str, importing_file: &Path) -> Option<PathBuf> {
+        let rest = module.trim_start_matches('.');
+        let dots = module.len() - rest.len();
+        let mut base = if dots == 0 {
+            self.root.to_path_buf()
+        } else {
+            importing_file.parent()?.to_path_buf()
+        };
+        for _ in 1..dots {
+            base = base.parent()?.to_path_buf();
+        }
+        let base = rest
+            .split('.')
+            .filter(|s| !s.is_empty())
+            .fold(base, |b, s| b.join(s));
+        [base.with_extension("py"), base.join("__init__.py")]
+            .into_iter()
+            .find(|candidate| self.files.contains(candidate.as_path()))
+    }
+}
+
+/// A pytest fixture, by the name tests request it with
+struct Fixture {
+    id: NodeId,
+    name: String,
+    file: PathBuf,
+}
+
+/// Tag framework entry points and add dependency and fixture `References`
+/// edges. Safe to run again over an already linked graph.
+pub fn link(graph: &mut CodeGraph, frameworks: &Frameworks, root: &Path) {
+    let mut entry_points: Vec<(NodeId, String)> = Vec::new();
+    let mut edges: Vec<(NodeId, NodeId, Confidence, usize)> = Vec::new();
+    {
+        let index = Index::build(graph, root);
+        let mut fixtures: HashMap<&Path, Vec<Fixture>> = HashMap::new();
+        let mut consumers: Vec<(NodeId, &Node)> = Vec::new();
+
+        for (id, node) in graph.nodes() {
+            let file = node.file_path().as_path();
+            if !matches!(node.kind(), NodeKind::Function | NodeKind::Class) || !is_python(file) {
+                continue;
+            }
+            let decorators = node.decorators();
+            if entry_point(node).is_none() {
+                if let Some(framework) = decorators.iter().find_map(|d| frameworks.registering(d)) {
+                    entry_points.push((id, framework.to_string()));
+                }
+            }
+
+            // Dependency calls in decorators and parameter defaults
+            let defaults = match node.data() {
+                NodeData::Function { parameters, .. } => parameters
+                    .iter()
+                    .filter_map(|p| p.default_value.as_deref())
+                    .collect(),
+                _ => Vec::new(),
+            };
+            for text in decorators.iter().map(String::as_str).chain(defaults) {
+                for name in frameworks.dependencies(text) {
+                    if let Some(target) = index.resolve(file, name).filter(|&t| t != id) {
+                        edges.push((id, target, Confidence::High, node.line()));
+                    }
+                }
+            }
+
+            let fixture = decorators
+                .iter()
+                .find(|d| FIXTURE_DECORATORS.contains(&callee(d)));
+            if let Some(decorator) = fixture {
+                let name = fixture_name(decorator).unwrap_or_else(|| node.name().to_string());
+                let dir = file.parent().unwrap_or(Path::new(""));
+                fixtures.entry(dir).or_default().push(Fixture {
+                    id,
+                    name,
+                    file: file.to_path_buf(),
+                });
+            }
+            if fixture.is_some() || node.name().starts_with("test") {
+                consumers.push((id, node));
+            }
+        }
+
+        for (id, node) in consumers {
+            let NodeData::Function { parameters, .. } = node.data() else {
+                continue;
+            };
+            for parameter in parameters {
+                if NOT_FIXTURES.contains(&parameter.name.as_str()) {
+                    continue;
+                }
+                if let Some(target) = find_fixture(&fixtures, id, node.file_path(), &parameter.name)
+                {
+                    edges.push((id, target, Confidence::Medium, node.line()));
+                }
+            }
+        }
+    }
+
+    for (id, framework) in entry_points {
+        if let Some(node) = graph.node_mut(id) {
+            let mut decorators = node.decorators().to_vec();
+            decorators.push(format!("{}{}", ENTRY_POINT_PREFIX, framework));
+            node.set_decorators(decorators);
+        }
+    }
+    for (from, to, confidence, line) in edges {
+        let linked = graph
+            .edges_from(from)
+            .any(|(target, e)| target == to && e.kind() == &EdgeKind::References);
+        if !linked {
+            graph.add_edge(
+                from,
+                to,
+                Edge::with_metadata(
+                    EdgeKind::References,
+                    EdgeMetadata::Reference { line, confidence },
+                ),
+            );
+        }
+    }
+}
+
+/// `pytest.fixture(name="db")` → `db`
+fn fixture_name(decorator: &str) -> Option<String> {
+    static NAME: OnceLock<Regex> = OnceLock::new();
+    let re = NAME.get_or_init(|| Regex::new(r#"\bname\s*=\s*["']([^"']+)["']"#).unwrap());
+    let (_, args) = decorator.split_once('(')?;
+    Some(re.captures(args)?[1].to_string())
+}
+
+/// The fixture a parameter named `name` of `consumer` requests: one in the
+/// same file, else in the same package directory, else in the `conftest.py`
+/// of the nearest enclosing directory that has one
+fn find_fixture(
+    fixtures: &HashMap<&Path, Vec<Fixture>>,
+    consumer: NodeId,
+    file: &Path,
+    name: &str,
+) -> Option<NodeId> {
+    let candidates = |dir: &Path| {
+        fixtures
+            .get(dir)
+            .into_iter()
+            .flatten()
+            .filter(move |f| f.name == name && f.id != consumer)
+    };
+    let dir = file.parent()?;
+    candidates(dir)
+        .find(|f| f.file == file)
+        .or_else(|| candidates(dir).next())
+        .or_else(|| {
+            dir.ancestors()
+                .skip(1)
+                .find_map(|d| candidates(d).find(|f| f.file.ends_with("conftest.py")))
+        })
+        .map(|f| f.id)
+}
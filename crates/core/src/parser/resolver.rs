@@ -5,27 +5,93 @@
 //! absolute file path that exists in the graph, then adds:
 //!
 //! - A file-level [`EdgeKind::Imports`] edge from the importing file node to the
-//!   target file node.
+//!   target file node, tagged with an [`ImportKind`]: the parser's (type-only
+//!   imports), else `test` when the target file is test code.
 //! - A per-symbol [`EdgeKind::References`] edge from the [`NodeKind::Import`] node
 //!   to each resolved target symbol node.
 //! - A [`EdgeKind::Calls`] edge for every [`UnresolvedCall`] whose callee can be
 //!   matched to a symbol in the resolved target file.
+//! - For calls into modules outside the repository (standard library,
+//!   packages), a [`EdgeKind::Calls`] edge to the caller's import of the
+//!   module, carrying [`EdgeMetadata::ExternalCall`] with the callee's name.
+//!
+//! Imports that resolve to no file are marked `dev` when the owning manifest
+//! declares the package only as a development dependency (see [`dev_deps`]).
+//! The file → import edge carries the same kind as the file → file edge.
+//!
+//! Go calls resolve package-wide: the callee may live in any file of the
+//! target package directory. When several build-constrained variants define
+//! the callee (e.g. `proc_linux.go` / `proc_windows.go`), the call links to
+//! every variant compiled for the configured platforms (all of them if none
+//! are configured).
+//!
+//! C# calls resolve by type: the parser records the type the callee is a
+//! member of (`Order.Create()`, `new Order()`, or an unqualified call inside
+//! `Order`) and the callee may live in any `.cs` file declaring that type.
+//! Members added by source generators are synthesized before calls resolve
+//! (see [`csharp_generated`]), and the declarations of each partial type are
+//! merged afterwards (see [`csharp_partials`]).
+//!
+//! Python functions are then linked to the frameworks that invoke them and
+//! the dependencies those frameworks inject (see [`python_frameworks`]).
+//!
+//! After an edit, [`CrossFileResolver::resolve_incremental`] applies only the
+//! resolutions touching the re-parsed files (see [`super::incremental`]).
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use crate::graph::{CodeGraph, Edge, EdgeKind, EdgeMetadata, NodeData, NodeId, NodeKind};
+use crate::code_kind::CodeKind;
+use crate::finding::Confidence;
+use crate::graph::{
+    CodeGraph, Edge, EdgeKind, EdgeMetadata, ImportKind, NodeData, NodeId, NodeKind,
+};
 
+use super::csharp_generated::{self, Generators};
+use super::csharp_partials;
+use super::dev_deps::DevDeps;
+use super::go_build::{self, Platform};
+use super::python_frameworks::{self, Frameworks};
 use super::{UnresolvedCall, UnresolvedImport};
 
+/// (importing file, module) → import nodes of that module outside the repo,
+/// with the names each imports
+type ExternalImports = HashMap<(PathBuf, String), Vec<(NodeId, Vec<String>)>>;
+
 /// Resolves collected import/call records into concrete cross-file graph edges.
 pub struct CrossFileResolver<'a> {
     root: &'a Path,
+    go_platforms: Vec<Platform>,
+    python_frameworks: Frameworks,
+    csharp_generators: Generators,
 }
 
 impl<'a> CrossFileResolver<'a> {
     pub fn new(root: &'a Path) -> Self {
-        Self { root }
+        Self {
+            root,
+            go_platforms: Vec::new(),
+            python_frameworks: Frameworks::builtin(),
+            csharp_generators: Generators::builtin(),
+        }
+    }
+
+    /// Prefer Go build variants compiled for these platforms when resolving calls
+    pub fn with_go_platforms(mut self, platforms: Vec<Platform>) -> Self {
+        self.go_platforms = platforms;
+        self
+    }
+
+    /// Link Python functions with this framework table instead of the built-in one
+    pub fn with_python_frameworks(mut self, frameworks: Frameworks) -> Self {
+        self.python_frameworks = frameworks;
+        self
+    }
+
+    /// Synthesize C# generated members with this table instead of the built-in one
+    pub fn with_csharp_generators(mut self, generators: Generators) -> Self {
+        self.csharp_generators = generators;
+        self
     }
 
     /// Run resolution over the merged graph.
@@ -37,6 +103,40 @@ impl<'a> CrossFileResolver<'a> {
         graph: &mut CodeGraph,
         imports: Vec<UnresolvedImport>,
         calls: Vec<UnresolvedCall>,
+    ) {
+        csharp_generated::link(graph, &self.csharp_generators);
+        self.resolve_scoped(graph, imports, calls, None);
+        csharp_partials::merge(graph);
+        python_frameworks::link(graph, &self.python_frameworks, self.root);
+    }
+
+    /// Re-run resolution after the files in `changed` were removed from an
+    /// already resolved graph and parsed back into it.
+    ///
+    /// `imports` and `calls` are the records of every file. Only what
+    /// touches `changed` is applied: the edges from and into their nodes,
+    /// and the import classification of their own imports; everything else
+    /// is still in the graph. The C# passes are not re-run, so a graph with
+    /// C# files needs a full [`resolve`](Self::resolve) instead.
+    pub fn resolve_incremental(
+        &self,
+        graph: &mut CodeGraph,
+        imports: Vec<UnresolvedImport>,
+        calls: Vec<UnresolvedCall>,
+        changed: &HashSet<PathBuf>,
+    ) {
+        self.resolve_scoped(graph, imports, calls, Some(changed));
+        python_frameworks::link(graph, &self.python_frameworks, self.root);
+    }
+
+    /// Resolve `imports` and `calls`, keeping only the edges and updates
+    /// touching a file in `scope` (all of them without one)
+    fn resolve_scoped(
+        &self,
+        graph: &mut CodeGraph,
+        imports: Vec<UnresolvedImport>,
+        calls: Vec<UnresolvedCall>,
+        scope: Option<&HashSet<PathBuf>>,
     ) {
         // Build file-path → file NodeId index
         let file_index: HashMap<PathBuf, NodeId> = graph
@@ -62,21 +162,110 @@ impl<'a> CrossFileResolver<'a> {
             })
             .collect();
 
+        // Build (package_dir, symbol_name) → [NodeId] index for Go symbols,
+        // keeping only variants active on the configured platforms when any match
+        let mut go_package_index: HashMap<(PathBuf, String), Vec<NodeId>> = HashMap::new();
+        for ((path, name), &id) in &symbol_index {
+            if path.extension().and_then(|e| e.to_str()) != Some("go") {
+                continue;
+            }
+            let Some(dir) = path.parent() else {
+                continue;
+            };
+            go_package_index
+                .entry((dir.to_path_buf(), name.clone()))
+                .or_default()
+                .push(id);
+        }
+        for ids in go_package_index.values_mut() {
+            ids.sort();
+            let active: Vec<NodeId> = ids
+                .iter()
+                .copied()
+                .filter(|&id| {
+                    graph
+                        .node(id)
+                        .is_some_and(|n| go_build::is_active(n, &self.go_platforms))
+                })
+                .collect();
+            if !active.is_empty() {
+                *ids = active;
+            }
+        }
+
+        // Build (type, member) → [NodeId] index for C# members in any file, and
+        // type → [NodeId] for C# types (the callee of `new T()` without a
+        // declared constructor)
+        let mut csharp_members: HashMap<(String, String), Vec<NodeId>> = HashMap::new();
+        let mut csharp_types: HashMap<String, Vec<NodeId>> = HashMap::new();
+        for (id, node) in graph.nodes() {
+            if !is_csharp(node.file_path()) {
+                continue;
+            }
+            match node.kind() {
+                NodeKind::Function => {
+                    if let Some((owner, member)) = node.name().rsplit_once('.') {
+                        csharp_members
+                            .entry((owner.to_string(), member.to_string()))
+                            .or_default()
+                            .push(id);
+                    }
+                }
+                NodeKind::Class => csharp_types
+                    .entry(node.name().to_string())
+                    .or_default()
+                    .push(id),
+                _ => {}
+            }
+        }
+
         let mut edges_to_add: Vec<(NodeId, NodeId, Edge)> = Vec::new();
         let mut import_resolutions: Vec<(NodeId, PathBuf)> = Vec::new();
 
         // ── Resolve imports ──────────────────────────────────────────────────
+        let mut dev_deps = DevDeps::new(self.root);
+        let mut import_kinds: Vec<(NodeId, NodeId, ImportKind)> = Vec::new();
+        let mut external_imports = ExternalImports::new();
         for imp in imports {
             let Some(target_path) =
                 self.resolve_module(&imp.module_specifier, &imp.importing_file, &file_index)
             else {
-                continue; // external / unresolvable module
+                external_imports
+                    .entry((imp.importing_file.clone(), imp.module_specifier.clone()))
+                    .or_default()
+                    .push((imp.import_node_id, imp.imported_names.clone()));
+                // External / unresolvable module: a dev dependency or nothing
+                if imp.kind.is_runtime()
+                    && dev_deps.is_dev(&imp.importing_file, &imp.module_specifier)
+                {
+                    import_kinds.push((
+                        imp.importing_file_node_id,
+                        imp.import_node_id,
+                        ImportKind::Dev,
+                    ));
+                }
+                continue;
             };
 
             let Some(&target_file_id) = file_index.get(&target_path) else {
                 continue;
             };
 
+            let target_is_test = matches!(
+                graph.node(target_file_id).map(|n| n.data()),
+                Some(NodeData::File { code_kind, .. }) if *code_kind == CodeKind::Test
+            );
+            let kind = if imp.kind.is_runtime() && target_is_test {
+                import_kinds.push((
+                    imp.importing_file_node_id,
+                    imp.import_node_id,
+                    ImportKind::Test,
+                ));
+                ImportKind::Test
+            } else {
+                imp.kind
+            };
+
             import_resolutions.push((imp.import_node_id, target_path.clone()));
 
             // File-level Imports edge
@@ -88,6 +277,7 @@ impl<'a> CrossFileResolver<'a> {
                     EdgeMetadata::Import {
                         alias: None,
                         is_wildcard: imp.is_wildcard,
+                        kind,
                     },
                 ),
             ));
@@ -111,14 +301,86 @@ impl<'a> CrossFileResolver<'a> {
 
         // ── Resolve cross-file calls ─────────────────────────────────────────
         for call in calls {
+            if is_csharp(&call.importing_file) {
+                // A call on another type also uses the type itself
+                let caller_type = graph
+                    .node(call.caller_node_id)
+                    .and_then(|n| n.name().rsplit_once('.'))
+                    .map(|(owner, _)| owner);
+                if caller_type != Some(call.module_specifier.as_str()) {
+                    for type_id in csharp_type(&csharp_types, &call.module_specifier) {
+                        edges_to_add.push((
+                            call.caller_node_id,
+                            type_id,
+                            Edge::with_metadata(
+                                EdgeKind::References,
+                                EdgeMetadata::Reference {
+                                    line: call.call_line,
+                                    confidence: Confidence::High,
+                                },
+                            ),
+                        ));
+                    }
+                }
+                for callee_id in csharp_callees(&csharp_members, &csharp_types, &call) {
+                    edges_to_add.push((
+                        call.caller_node_id,
+                        callee_id,
+                        Edge::with_metadata(
+                            EdgeKind::Calls,
+                            EdgeMetadata::Call {
+                                line: call.call_line,
+                                is_direct: true,
+                            },
+                        ),
+                    ));
+                }
+                continue;
+            }
+
             let Some(target_path) =
                 self.resolve_module(&call.module_specifier, &call.importing_file, &file_index)
             else {
+                let key = (call.importing_file.clone(), call.module_specifier.clone());
+                if let Some(import_id) = external_imports.get(&key).and_then(|candidates| {
+                    // The import naming the callee, else the module import
+                    candidates
+                        .iter()
+                        .find(|(_, names)| names.contains(&call.callee_name))
+                        .or(candidates.first())
+                        .map(|(id, _)| *id)
+                }) {
+                    edges_to_add.push((
+                        call.caller_node_id,
+                        import_id,
+                        Edge::with_metadata(
+                            EdgeKind::Calls,
+                            EdgeMetadata::ExternalCall {
+                                line: call.call_line,
+                                callee: call.callee_name,
+                            },
+                        ),
+                    ));
+                }
                 continue;
             };
 
-            let key = (target_path, call.callee_name.clone());
-            if let Some(&callee_id) = symbol_index.get(&key) {
+            let is_go = target_path.extension().and_then(|e| e.to_str()) == Some("go");
+            let callee_ids: Vec<NodeId> = if is_go {
+                let dir = target_path.parent().unwrap_or(Path::new("")).to_path_buf();
+                go_package_index
+                    .get(&(dir, call.callee_name.clone()))
+                    .cloned()
+                    .unwrap_or_default()
+            } else {
+                symbol_index
+                    .get(&(target_path, call.callee_name.clone()))
+                    .copied()
+                    .into_iter()
+                    .collect()
+            };
+
+            for callee_id in callee_ids {
                 edges_to_add.push((
                     call.caller_node_id,
                     callee_id,
@@ -133,11 +395,31 @@ impl<'a> CrossFileResolver<'a> {
             }
         }
 
+        // Out of scope, the graph already has everything not touching it
+        if let Some(scope) = scope {
+            let touches = |id: NodeId| {
+                graph
+                    .node(id)
+                    .is_some_and(|n| scope.contains(n.file_path()))
+            };
+            edges_to_add.retain(|(from, to, _)| touches(*from) || touches(*to));
+            import_kinds.retain(|(file, _, _)| touches(*file));
+            import_resolutions.retain(|(import, _)| touches(*import));
+        }
+
         // Apply edges (must not borrow graph mutably above)
         for (from, to, edge) in edges_to_add {
             graph.add_edge(from, to, edge);
         }
 
+        // Classify imports of test code and dev-only packages on the
+        // file → import edges too
+        for (file, import, kind) in import_kinds {
+            if let Some(edge) = graph.edge_mut(file, import) {
+                edge.set_import_kind(kind);
+            }
+        }
+
         // Stamp resolved_path on Import nodes
         for (import_node_id, resolved_path) in import_resolutions {
             if let Some(node) = graph.node_mut(import_node_id) {
@@ -152,7 +434,7 @@ impl<'a> CrossFileResolver<'a> {
         }
     }
 
-    // ── Module path resolution ───────────────────────────────────────────────
+    // ── Module path resolution ───────────────────────────────────────────────────
 
     fn resolve_module(
         &self,
@@ -237,3 +519,41 @@ impl<'a> CrossFileResolver<'a> {
         None
     }
 }
+
+fn is_csharp(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("cs")
+}
+
+/// The members a C# call resolves to. `module_specifier` is the type the
+/// callee is a member of, as written (`Order`, `Models.Order`,
+/// `Outer.Inner`); a call whose callee is named like the type is a
+/// constructor call and falls back to the type itself.
+fn csharp_callees(
+    members: &HashMap<(String, String), Vec<NodeId>>,
+    types: &HashMap<String, Vec<NodeId>>,
+    call: &UnresolvedCall,
+) -> Vec<NodeId> {
+    let written = call.module_specifier.as_str();
+    let simple = written.rsplit('.').next().unwrap_or(written);
+    for owner in [written, simple] {
+        if let Some(ids) = members.get(&(owner.to_string(), call.callee_name.clone())) {
+            return ids.clone();
+        }
+        if call.callee_name == simple {
+            if let Some(ids) = types.get(owner) {
+                return ids.clone();
+            }
+        }
+    }
+    Vec::new()
+}
+
+/// The C# type nodes a type name as written refers to
+fn csharp_type(types: &HashMap<String, Vec<NodeId>>, written: &str) -> Vec<NodeId> {
+    let simple = written.rsplit('.').next().unwrap_or(written);
+    types
+        .get(written)
+        .or_else(|| types.get(simple))
+        .cloned()
+        .unwrap_or_default()
+}
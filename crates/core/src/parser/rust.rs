@@ -1,5 +1,6 @@
 //! Rust language parser using Tree-sitter
 
+use super::docs::{self, DocStyle};
 use super::{
     build_function_nodes_map, build_imports_map, collect_import_state, resolve_import_call,
     LanguageParser, ParseError, ParseState, UnresolvedCall,
@@ -86,6 +87,7 @@ impl RustParser {
             0,
             NodeData::File {
                 language: "rust".to_string(),
+                code_kind: crate::code_kind::classify(file_path, graph.root_path(), source),
             },
         );
         let file_node_id = graph.add_node(file_node);
@@ -991,6 +993,10 @@ impl LanguageParser for RustParser {
         &[".rs"]
     }
 
+    fn doc_style(&self) -> Option<DocStyle> {
+        Some(DocStyle::Rust)
+    }
+
     fn parse_file(
         &self,
         file_path: &Path,
@@ -1007,17 +1013,20 @@ impl LanguageParser for RustParser {
         graph: &mut CodeGraph,
     ) -> Result<Vec<NodeId>, ParseError> {
         let tree = self.parse_tree(source)?;
-        Ok(self.extract_nodes(&tree, source, file_path, graph))
+        let ids = self.extract_nodes(&tree, source, file_path, graph);
+        docs::record(graph, file_path, source, DocStyle::Rust);
+        Ok(ids)
     }
 
-    fn parse_file_with_state(
+    fn parse_source_with_state(
         &self,
+        source: &str,
         file_path: &Path,
         graph: &mut CodeGraph,
     ) -> Result<(Vec<NodeId>, ParseState), ParseError> {
-        let source = std::fs::read_to_string(file_path)?;
-        let tree = self.parse_tree(&source)?;
-        let ids = self.extract_nodes(&tree, &source, file_path, graph);
+        let tree = self.parse_tree(source)?;
+        let ids = self.extract_nodes(&tree, source, file_path, graph);
+        docs::record(graph, file_path, source, DocStyle::Rust);
 
         let mut state = collect_import_state(graph, file_path);
 
@@ -1026,7 +1035,7 @@ impl LanguageParser for RustParser {
             let function_nodes = build_function_nodes_map(graph, file_path);
             state.unresolved_calls = self.collect_cross_file_calls(
                 &tree,
-                &source,
+                source,
                 file_path,
                 &function_nodes,
                 &imports_map,
@@ -71,6 +71,7 @@ impl SwiftParser {
             0,
             NodeData::File {
                 language: "swift".to_string(),
+                code_kind: crate::code_kind::classify(file_path, graph.root_path(), source),
             },
         );
         let file_node_id = graph.add_node(file_node);
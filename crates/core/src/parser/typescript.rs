@@ -1,11 +1,13 @@
 //! TypeScript/JavaScript language parser using Tree-sitter
 
+use super::docs::{self, DocStyle};
 use super::{
     build_function_nodes_map, build_imports_map, collect_import_state, resolve_import_call,
     LanguageParser, ParseError, ParseState, UnresolvedCall,
 };
 use crate::graph::{
-    CodeGraph, Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeId, NodeKind, Parameter,
+    CodeGraph, Edge, EdgeKind, EdgeMetadata, ImportKind, Node, NodeData, NodeId, NodeKind,
+    Parameter,
 };
 use std::collections::HashMap;
 use std::path::Path;
@@ -78,6 +80,7 @@ impl TypeScriptParser {
             0,
             NodeData::File {
                 language: "typescript".to_string(),
+                code_kind: crate::code_kind::classify(file_path, graph.root_path(), source),
             },
         );
         let file_node_id = graph.add_node(file_node);
@@ -167,7 +170,7 @@ impl TypeScriptParser {
             }
             "import_statement" => {
                 if let Some(node_id) = self.extract_import(child, source, file_path, graph) {
-                    graph.add_edge(file_node_id, node_id, Edge::new(EdgeKind::Imports));
+                    graph.add_edge(file_node_id, node_id, Edge::import(import_kind(child)));
                     node_ids.push(node_id);
                 }
             }
@@ -1292,6 +1295,10 @@ impl LanguageParser for TypeScriptParser {
         &[".ts", ".tsx", ".js", ".jsx"]
     }
 
+    fn doc_style(&self) -> Option<DocStyle> {
+        Some(DocStyle::JsDoc)
+    }
+
     fn parse_file(
         &self,
         file_path: &Path,
@@ -1308,17 +1315,20 @@ impl LanguageParser for TypeScriptParser {
         graph: &mut CodeGraph,
     ) -> Result<Vec<NodeId>, ParseError> {
         let tree = self.parse_tree(source)?;
-        Ok(self.extract_nodes(&tree, source, file_path, graph))
+        let ids = self.extract_nodes(&tree, source, file_path, graph);
+        docs::record(graph, file_path, source, DocStyle::JsDoc);
+        Ok(ids)
     }
 
-    fn parse_file_with_state(
+    fn parse_source_with_state(
         &self,
+        source: &str,
         file_path: &Path,
         graph: &mut CodeGraph,
     ) -> Result<(Vec<NodeId>, ParseState), ParseError> {
-        let source = std::fs::read_to_string(file_path)?;
-        let tree = self.parse_tree(&source)?;
-        let ids = self.extract_nodes(&tree, &source, file_path, graph);
+        let tree = self.parse_tree(source)?;
+        let ids = self.extract_nodes(&tree, source, file_path, graph);
+        docs::record(graph, file_path, source, DocStyle::JsDoc);
 
         let mut state = collect_import_state(graph, file_path);
 
@@ -1327,7 +1337,7 @@ impl LanguageParser for TypeScriptParser {
             let function_nodes = build_function_nodes_map(graph, file_path);
             state.unresolved_calls = self.collect_cross_file_calls(
                 &tree,
-                &source,
+                source,
                 file_path,
                 &function_nodes,
                 &imports_map,
@@ -1337,3 +1347,40 @@ impl LanguageParser for TypeScriptParser {
         Ok((ids, state))
     }
 }
+
+/// `type_only` for `import type … from` and for named imports that are all
+/// `type` (`import { type A, type B } from`); a default or namespace import
+/// next to them is a runtime value
+fn import_kind(node: &tree_sitter::Node) -> ImportKind {
+    let mut cursor = node.walk();
+    let children: Vec<tree_sitter::Node> = node.children(&mut cursor).collect();
+    if children.iter().any(|c| c.kind() == "type") {
+        return ImportKind::TypeOnly;
+    }
+    let Some(clause) = children.iter().find(|c| c.kind() == "import_clause") else {
+        return ImportKind::Runtime;
+    };
+    let mut cursor = clause.walk();
+    let mut specifiers = 0;
+    for part in clause.children(&mut cursor) {
+        if part.kind() != "named_imports" {
+            return ImportKind::Runtime;
+        }
+        let mut inner = part.walk();
+        for spec in part
+            .children(&mut inner)
+            .filter(|s| s.kind() == "import_specifier")
+        {
+            let mut spec_cursor = spec.walk();
+            if !spec.children(&mut spec_cursor).any(|c| c.kind() == "type") {
+                return ImportKind::Runtime;
+            }
+            specifiers += 1;
+        }
+    }
+    if specifiers > 0 {
+        ImportKind::TypeOnly
+    } else {
+        ImportKind::Runtime
+    }
+}
@@ -0,0 +1,133 @@
+//! In-process analysis of a whole tree under a fixed config
+//!
+//! [`analyze_root`] runs the deterministic part of the review pipeline —
+//! discovery, parsing, content and graph analyzers, the `[files] generated`
+//! policy, code kinds, code suggestion checks, the suppression audit, inline
+//! and per-path suppression — and returns the findings instead of printing them. It reads
+//! no `.revet.toml` (the caller passes the config), writes no cache, and
+//! skips the steps that
+//! depend on git history or the network (diff scoping, hotspots, history
+//! secrets, baselines, AI). The same tree and config therefore always give
+//! the same findings, which is what the `revet-eval` harness and embedders
+//! need.
+//!
+//! [`Analysis::ai_estimate`] prices AI enrichment of the result the way
+//! `revet review --ai --full --estimate-only` does, from the same graph.
+
+use crate::ai::{self, AiEstimate, PriorityContext};
+use crate::analyzer::AnalyzerDispatcher;
+use crate::baseline::Baseline;
+use crate::code_kind::CodeKinds;
+use crate::config::RevetConfig;
+use crate::discovery::discover_files_with_dotfiles;
+use crate::finding::Finding;
+use crate::generated::GeneratedFiles;
+use crate::graph::CodeGraph;
+use crate::parser::ParserDispatcher;
+use crate::storage::StoragePaths;
+use crate::suggestion;
+use crate::suppress::{
+    filter_findings_by_path_rules, filter_inline_with, Constructs, SuppressedFinding,
+};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Result of [`analyze_root`]
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    /// Canonical root the analysis ran on; finding paths are under it
+    pub root: PathBuf,
+    /// Files analyzed, absolute and sorted
+    pub files: Vec<PathBuf>,
+    /// Findings left after suppression, IDs numbered per prefix
+    pub findings: Vec<Finding>,
+    /// Findings silenced by `revet-ignore` comments or `[ignore.per_path]`
+    pub suppressed: Vec<SuppressedFinding>,
+    /// Files that failed to parse, as `"<path>: <error>"`
+    pub parse_errors: Vec<String>,
+    /// Code graph of every file, generated ones included
+    pub graph: CodeGraph,
+}
+
+impl Analysis {
+    /// What `--ai` would cost on these findings under `max_cost`, scored with
+    /// the graph, the saved baseline and `[risk] critical_paths` like a review
+    pub fn ai_estimate(&self, config: &RevetConfig, max_cost: f64) -> Result<AiEstimate> {
+        let baseline = Baseline::load_at(&StoragePaths::resolve(&self.root, config))
+            .ok()
+            .flatten();
+        let context = PriorityContext {
+            graph: Some(&self.graph),
+            baseline: baseline.as_ref(),
+            critical_paths: &config.risk.critical_paths,
+        };
+        let plan = ai::plan(&self.findings, &self.root, &config.ai, max_cost, &context)?;
+        Ok(AiEstimate::new(&plan, &config.ai, max_cost))
+    }
+}
+
+/// Analyze every file under `root` with `config`
+pub fn analyze_root(root: &Path, config: &RevetConfig) -> Result<Analysis> {
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("Cannot resolve {}", root.display()))?;
+    let parsers = ParserDispatcher::new_with_config(config);
+    let analyzers = AnalyzerDispatcher::new_with_config(config);
+
+    let mut extensions = parsers.supported_extensions();
+    for ext in analyzers.extra_extensions(config) {
+        if !extensions.contains(&ext) {
+            extensions.push(ext);
+        }
+    }
+    let filenames = analyzers.extra_filenames(config);
+    let dotfiles = analyzers.extra_dotfiles(config);
+    let files = discover_files_with_dotfiles(
+        &root,
+        &extensions,
+        &filenames,
+        &dotfiles,
+        &config.ignore.paths,
+    )?;
+    if files.is_empty() {
+        return Ok(Analysis {
+            graph: CodeGraph::new(root.clone()),
+            root,
+            files,
+            findings: Vec::new(),
+            suppressed: Vec::new(),
+            parse_errors: Vec::new(),
+        });
+    }
+
+    // Generated files are parsed either way so their symbols resolve
+    let generated = GeneratedFiles::from_config(&config.files).classify(&files);
+    let analysis_files = generated.analysis_files(&files);
+    let (graph, parse_errors) = parsers.parse_files_parallel(&files, root.clone());
+    let mut findings = analyzers.run_all_parallel(&analysis_files, &root, config);
+    findings.extend(analyzers.run_graph_analyzers(&graph, config));
+    let findings = generated.apply(findings, &root);
+    let mut findings = CodeKinds::from_config(&config.files).apply(findings, &root);
+    suggestion::verify(&mut findings, &root);
+
+    let inline = filter_inline_with(findings, &Constructs::from_graph(&graph));
+    let (audit_findings, audit_suppressed) =
+        analyzers.run_suppression_audit(&analysis_files, &inline.usage, true, config);
+    let mut findings = inline.kept;
+    findings.extend(audit_findings);
+    let mut suppressed = inline.suppressed;
+    suppressed.extend(audit_suppressed);
+
+    let (findings, path_suppressed) =
+        filter_findings_by_path_rules(findings, &config.ignore.per_path, &root);
+    suppressed.extend(path_suppressed);
+
+    Ok(Analysis {
+        root,
+        files,
+        findings,
+        suppressed,
+        parse_errors,
+        graph,
+    })
+}
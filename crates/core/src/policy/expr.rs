@@ -0,0 +1,607 @@
+//! Filter expressions of `[[policy]] filter`
+//!
+//! ```text
+//! expr   := and ("or" and)*
+//! and    := unary ("and" unary)*
+//! unary  := "not" unary | "(" expr ")" | field op value
+//! op     := "==" | "!=" | "~" | "!~" | "<" | "<=" | ">" | ">="
+//! value  := "double quoted" | 'single quoted' | bare-word
+//! ```
+//!
+//! `not` binds tighter than `and`, which binds tighter than `or`. `~` and
+//! `!~` match a glob (`payments/**`, `SEC-*`); the ordering operators only
+//! apply to `severity` (info < warning < error) and `confidence` (low <
+//! medium < high). `tag == fixable` holds when the finding carries the tag;
+//! `code_kind == test` when it is in test code.
+//! Values are checked while parsing, so a typo such as `severity == eror`
+//! is a parse error rather than a policy that never matches.
+
+use super::{Status, Subject};
+use crate::code_kind::CodeKind;
+use crate::finding::{Confidence, Severity};
+use std::fmt;
+
+/// A finding field an expression can test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    /// Finding ID, e.g. `SEC-003`
+    Id,
+    /// ID prefix, e.g. `SEC`
+    Prefix,
+    /// Qualified sub-rule, e.g. `error_handling:swallowed`
+    Rule,
+    /// Module of the sub-rule, e.g. `error_handling`
+    Module,
+    Severity,
+    Confidence,
+    /// Path relative to the repository root, `/`-separated
+    Path,
+    /// `new`, `baselined` or `suppressed`
+    Status,
+    /// `generated`, `fixable` or `ai_false_positive`
+    Tag,
+    /// `production`, `test` or `script`
+    CodeKind,
+    Message,
+    /// External tool of an imported finding, e.g. `gitleaks`
+    Tool,
+}
+
+impl Field {
+    const ALL: &'static [(&'static str, Field)] = &[
+        ("id", Field::Id),
+        ("prefix", Field::Prefix),
+        ("rule", Field::Rule),
+        ("module", Field::Module),
+        ("severity", Field::Severity),
+        ("confidence", Field::Confidence),
+        ("path", Field::Path),
+        ("status", Field::Status),
+        ("tag", Field::Tag),
+        ("code_kind", Field::CodeKind),
+        ("message", Field::Message),
+        ("tool", Field::Tool),
+    ];
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::ALL.iter().find(|(n, _)| *n == name).map(|(_, f)| *f)
+    }
+
+    fn name(self) -> &'static str {
+        Self::ALL
+            .iter()
+            .find(|(_, f)| *f == self)
+            .map_or("", |(n, _)| n)
+    }
+
+    fn names() -> String {
+        Self::ALL
+            .iter()
+            .map(|(n, _)| *n)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Tags a finding can carry
+pub const TAGS: &[&str] = &["generated", "fixable", "ai_false_positive"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Glob,
+    NotGlob,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    fn symbol(self) -> &'static str {
+        match self {
+            Op::Eq => "==",
+            Op::Ne => "!=",
+            Op::Glob => "~",
+            Op::NotGlob => "!~",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+        }
+    }
+
+    fn is_ordering(self) -> bool {
+        matches!(self, Op::Lt | Op::Le | Op::Gt | Op::Ge)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    Text(String),
+    Glob(glob::Pattern),
+    /// Severity or confidence, as a rank (higher is more severe/confident)
+    Rank(u8),
+    Status(Status),
+    Tag(String),
+    CodeKind(CodeKind),
+}
+
+/// A parsed filter expression
+#[derive(Debug, Clone)]
+pub struct Expr(Node);
+
+#[derive(Debug, Clone)]
+enum Node {
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Not(Box<Node>),
+    Compare { field: Field, op: Op, value: Value },
+}
+
+/// Why an expression did not parse, at a 1-based character column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExprError {
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+impl Expr {
+    /// Parse `source`
+    pub fn parse(source: &str) -> Result<Self, ExprError> {
+        let tokens = lex(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        if parser.peek().kind == Kind::End {
+            return Err(parser.error_here("empty expression"));
+        }
+        let expr = parser.or()?;
+        let token = parser.peek();
+        match &token.kind {
+            Kind::End => Ok(Expr(expr)),
+            Kind::RParen => Err(parser.error_here("unexpected `)` without a matching `(`")),
+            _ => Err(parser.error_here(format!(
+                "expected `and`, `or` or the end of the expression, found {}",
+                token.kind
+            ))),
+        }
+    }
+
+    /// Whether the expression tests `field` anywhere
+    pub fn mentions(&self, field: Field) -> bool {
+        self.0.mentions(field)
+    }
+
+    /// Whether `subject` matches the expression
+    pub fn matches(&self, subject: &Subject) -> bool {
+        self.0.matches(subject)
+    }
+}
+
+impl Node {
+    fn mentions(&self, field: Field) -> bool {
+        match self {
+            Node::And(a, b) | Node::Or(a, b) => a.mentions(field) || b.mentions(field),
+            Node::Not(e) => e.mentions(field),
+            Node::Compare { field: f, .. } => *f == field,
+        }
+    }
+
+    fn matches(&self, subject: &Subject) -> bool {
+        match self {
+            Node::And(a, b) => a.matches(subject) && b.matches(subject),
+            Node::Or(a, b) => a.matches(subject) || b.matches(subject),
+            Node::Not(e) => !e.matches(subject),
+            Node::Compare { field, op, value } => compare(subject, *field, *op, value),
+        }
+    }
+}
+
+fn compare(subject: &Subject, field: Field, op: Op, value: &Value) -> bool {
+    let finding = subject.finding;
+    match value {
+        Value::Rank(expected) => {
+            let actual = match field {
+                Field::Severity => severity_rank(finding.severity),
+                _ => confidence_rank(finding.confidence),
+            };
+            match op {
+                Op::Eq => actual == *expected,
+                Op::Ne => actual != *expected,
+                Op::Lt => actual < *expected,
+                Op::Le => actual <= *expected,
+                Op::Gt => actual > *expected,
+                Op::Ge => actual >= *expected,
+                Op::Glob | Op::NotGlob => false,
+            }
+        }
+        Value::Status(status) => (subject.status == *status) == (op == Op::Eq),
+        Value::Tag(tag) => super::tags(finding).contains(&tag.as_str()) == (op == Op::Eq),
+        Value::CodeKind(kind) => (finding.code_kind == *kind) == (op == Op::Eq),
+        Value::Text(text) => {
+            let actual = text_of(subject, field);
+            (actual.as_deref() == Some(text.as_str())) == (op == Op::Eq)
+        }
+        Value::Glob(pattern) => {
+            let actual = text_of(subject, field);
+            actual.is_some_and(|a| pattern.matches(&a)) == (op == Op::Glob)
+        }
+    }
+}
+
+/// The text of a string field; `None` for a rule-less finding's `rule`
+/// and `module`
+fn text_of(subject: &Subject, field: Field) -> Option<String> {
+    let finding = subject.finding;
+    match field {
+        Field::Id => Some(finding.id.clone()),
+        Field::Prefix => Some(crate::analyzer::registry::prefix_of(&finding.id).to_string()),
+        Field::Rule => finding.rule.clone(),
+        Field::Module => finding
+            .rule
+            .as_deref()
+            .map(|r| r.split(':').next().unwrap_or(r).to_string()),
+        Field::Path => Some(subject.path.to_string()),
+        Field::Message => Some(finding.message.clone()),
+        Field::Tool => finding.source_tool.clone(),
+        Field::Severity | Field::Confidence | Field::Status | Field::Tag | Field::CodeKind => None,
+    }
+}
+
+fn severity_rank(severity: Severity) -> u8 {
+    match severity {
+        Severity::Info => 0,
+        Severity::Warning => 1,
+        Severity::Error => 2,
+    }
+}
+
+fn confidence_rank(confidence: Confidence) -> u8 {
+    match confidence {
+        Confidence::Low => 0,
+        Confidence::Medium => 1,
+        Confidence::High => 2,
+    }
+}
+
+// ── Lexer ────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Word(String),
+    Quoted(String),
+    Op(Op),
+    LParen,
+    RParen,
+    End,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Word(w) => write!(f, "`{}`", w),
+            Kind::Quoted(s) => write!(f, "\"{}\"", s),
+            Kind::Op(op) => write!(f, "`{}`", op.symbol()),
+            Kind::LParen => write!(f, "`(`"),
+            Kind::RParen => write!(f, "`)`"),
+            Kind::End => write!(f, "the end of the expression"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: Kind,
+    /// 1-based character column
+    column: usize,
+}
+
+fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, '(' | ')' | '=' | '!' | '~' | '<' | '>' | '"' | '\'')
+}
+
+fn lex(source: &str) -> Result<Vec<Token>, ExprError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+        let next = chars.get(i + 1).copied();
+        let (kind, len) = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => (Kind::LParen, 1),
+            ')' => (Kind::RParen, 1),
+            '=' if next == Some('=') => (Kind::Op(Op::Eq), 2),
+            '!' if next == Some('=') => (Kind::Op(Op::Ne), 2),
+            '!' if next == Some('~') => (Kind::Op(Op::NotGlob), 2),
+            '~' => (Kind::Op(Op::Glob), 1),
+            '<' if next == Some('=') => (Kind::Op(Op::Le), 2),
+            '<' => (Kind::Op(Op::Lt), 1),
+            '>' if next == Some('=') => (Kind::Op(Op::Ge), 2),
+            '>' => (Kind::Op(Op::Gt), 1),
+            '=' => {
+                return Err(ExprError {
+                    column,
+                    message: "`=` is not an operator; use `==`".to_string(),
+                })
+            }
+            '!' => {
+                return Err(ExprError {
+                    column,
+                    message: "`!` is not an operator; use `not`, `!=` or `!~`".to_string(),
+                })
+            }
+            '"' | '\'' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .ok_or_else(|| ExprError {
+                        column,
+                        message: "unterminated string".to_string(),
+                    })?;
+                let text: String = chars[i + 1..i + 1 + end].iter().collect();
+                (Kind::Quoted(text), end + 2)
+            }
+            _ => {
+                let len = chars[i..]
+                    .iter()
+                    .position(|&ch| !is_word_char(ch))
+                    .unwrap_or(chars.len() - i);
+                (Kind::Word(chars[i..i + len].iter().collect()), len)
+            }
+        };
+        tokens.push(Token { kind, column });
+        i += len;
+    }
+    tokens.push(Token {
+        kind: Kind::End,
+        column: chars.len() + 1,
+    });
+    Ok(tokens)
+}
+
+// ── Parser ───────────────────────────────────────────────────────────────────
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos]
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.pos].clone();
+        if token.kind != Kind::End {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn keyword(&self, word: &str) -> bool {
+        matches!(&self.peek().kind, Kind::Word(w) if w == word)
+    }
+
+    fn error_here(&self, message: impl Into<String>) -> ExprError {
+        ExprError {
+            column: self.peek().column,
+            message: message.into(),
+        }
+    }
+
+    fn or(&mut self) -> Result<Node, ExprError> {
+        let mut expr = self.and()?;
+        while self.keyword("or") {
+            self.next();
+            expr = Node::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Node, ExprError> {
+        let mut expr = self.unary()?;
+        while self.keyword("and") {
+            self.next();
+            expr = Node::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Node, ExprError> {
+        if self.keyword("not") {
+            self.next();
+            return Ok(Node::Not(Box::new(self.unary()?)));
+        }
+        let token = self.next();
+        match token.kind {
+            Kind::LParen => {
+                let expr = self.or()?;
+                if self.peek().kind != Kind::RParen {
+                    return Err(match self.peek().kind {
+                        Kind::End => ExprError {
+                            column: token.column,
+                            message: "`(` is never closed".to_string(),
+                        },
+                        ref other => {
+                            self.error_here(format!("expected `and`, `or` or `)`, found {}", other))
+                        }
+                    });
+                }
+                self.next();
+                Ok(expr)
+            }
+            Kind::Word(ref name) => match Field::parse(name) {
+                Some(field) => self.comparison(field),
+                None => Err(ExprError {
+                    column: token.column,
+                    message: unknown_field(name),
+                }),
+            },
+            other => Err(ExprError {
+                column: token.column,
+                message: format!(
+                    "expected a field ({}), `not` or `(`, found {}",
+                    Field::names(),
+                    other
+                ),
+            }),
+        }
+    }
+
+    fn comparison(&mut self, field: Field) -> Result<Node, ExprError> {
+        let op_token = self.next();
+        let Kind::Op(op) = op_token.kind else {
+            return Err(ExprError {
+                column: op_token.column,
+                message: format!(
+                    "expected an operator (==, !=, ~, !~, <, <=, >, >=) after `{}`, found {}",
+                    field.name(),
+                    op_token.kind
+                ),
+            });
+        };
+        let value_token = self.next();
+        let text = match value_token.kind {
+            Kind::Word(w) | Kind::Quoted(w) => w,
+            other => {
+                return Err(ExprError {
+                    column: value_token.column,
+                    message: format!("expected a value after `{}`, found {}", op.symbol(), other),
+                })
+            }
+        };
+        let error = |message: String| ExprError {
+            column: value_token.column,
+            message,
+        };
+        let op_error = |allowed: &str| ExprError {
+            column: op_token.column,
+            message: format!(
+                "`{}` cannot compare `{}`; use {}",
+                op.symbol(),
+                field.name(),
+                allowed
+            ),
+        };
+
+        let value = match field {
+            Field::Severity | Field::Confidence => {
+                if matches!(op, Op::Glob | Op::NotGlob) {
+                    return Err(op_error("==, !=, <, <=, > or >="));
+                }
+                let rank = if field == Field::Severity {
+                    ["info", "warning", "error"].iter().position(|s| *s == text)
+                } else {
+                    ["low", "medium", "high"].iter().position(|s| *s == text)
+                };
+                let expected = if field == Field::Severity {
+                    "error, warning or info"
+                } else {
+                    "high, medium or low"
+                };
+                Value::Rank(rank.ok_or_else(|| {
+                    error(format!(
+                        "invalid {} `{}`; expected {}",
+                        field.name(),
+                        text,
+                        expected
+                    ))
+                })? as u8)
+            }
+            Field::Status => {
+                if op != Op::Eq && op != Op::Ne {
+                    return Err(op_error("== or !="));
+                }
+                Value::Status(Status::parse(&text).ok_or_else(|| {
+                    error(format!(
+                        "invalid status `{}`; expected new, baselined or suppressed",
+                        text
+                    ))
+                })?)
+            }
+            Field::Tag => {
+                if op != Op::Eq && op != Op::Ne {
+                    return Err(op_error("== or !="));
+                }
+                if !TAGS.contains(&text.as_str()) {
+                    return Err(error(format!(
+                        "invalid tag `{}`; expected {}",
+                        text,
+                        TAGS.join(", ")
+                    )));
+                }
+                Value::Tag(text)
+            }
+            Field::CodeKind => {
+                if op != Op::Eq && op != Op::Ne {
+                    return Err(op_error("== or !="));
+                }
+                Value::CodeKind(CodeKind::parse(&text).ok_or_else(|| {
+                    error(format!(
+                        "invalid code_kind `{}`; expected production, test or script",
+                        text
+                    ))
+                })?)
+            }
+            _ if op.is_ordering() => return Err(op_error("==, !=, ~ or !~")),
+            _ if matches!(op, Op::Glob | Op::NotGlob) => Value::Glob(
+                glob::Pattern::new(&text)
+                    .map_err(|e| error(format!("invalid glob `{}`: {}", text, e.msg)))?,
+            ),
+            _ => Value::Text(text),
+        };
+        Ok(Node::Compare { field, op, value })
+    }
+}
+
+/// Error message for an unknown field name, with the closest field
+fn unknown_field(name: &str) -> String {
+    let closest = Field::ALL
+        .iter()
+        .map(|(n, _)| (edit_distance(name, n), *n))
+        .min()
+        .filter(|(d, _)| *d <= 2);
+    match closest {
+        Some((_, suggestion)) => {
+            format!("unknown field `{}`; did you mean `{}`?", name, suggestion)
+        }
+        None => format!(
+            "unknown field `{}`; expected one of: {}",
+            name,
+            Field::names()
+        ),
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(cur)
+            };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
@@ -0,0 +1,399 @@
+//! Policy-as-code over the final findings (`[[policy]]`)
+//!
+//! Each policy selects findings with a filter expression (see [`expr`]),
+//! aggregates them (`count` or `exists`) and compares the aggregate to a
+//! threshold. Policies are evaluated after every filter — generated files,
+//! the confidence floor, inline suppressions, per-path rules and the
+//! baseline — so they see what the review reports. A filter that does not
+//! test `status` only matches reported (`new`) findings; one that does also
+//! sees the findings the baseline (`baselined`) or a suppression
+//! (`suppressed`) removed, which is how a policy can block regardless of
+//! the baseline.
+//!
+//! A violated `fail` policy fails the review independently of `--fail-on`
+//! and `[gate]`; `warn` and `require-escalation` are reported only.
+
+pub mod expr;
+
+pub use expr::{Expr, ExprError, Field};
+
+use crate::config::PolicyConfig;
+use crate::finding::{Finding, FixKind};
+use crate::suppress::SuppressedFinding;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Whether a finding is reported or was filtered out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// Reported by the review
+    New,
+    /// Filtered out by the baseline
+    Baselined,
+    /// Filtered out by an inline suppression or a per-path rule
+    Suppressed,
+}
+
+impl Status {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "new" => Some(Self::New),
+            "baselined" => Some(Self::Baselined),
+            "suppressed" => Some(Self::Suppressed),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::Baselined => "baselined",
+            Self::Suppressed => "suppressed",
+        }
+    }
+
+    fn of(suppressed: &SuppressedFinding) -> Self {
+        if suppressed.reason == "baseline" {
+            Self::Baselined
+        } else {
+            Self::Suppressed
+        }
+    }
+}
+
+/// A finding as a filter expression sees it
+pub struct Subject<'a> {
+    pub finding: &'a Finding,
+    /// Path relative to the repository root, `/`-separated
+    pub path: String,
+    pub status: Status,
+}
+
+impl<'a> Subject<'a> {
+    pub fn new(finding: &'a Finding, repo_root: &Path, status: Status) -> Self {
+        let rel = finding
+            .file
+            .strip_prefix(repo_root)
+            .unwrap_or(&finding.file);
+        Self {
+            finding,
+            path: rel.to_string_lossy().replace('\\', "/"),
+            status,
+        }
+    }
+}
+
+/// The tags of a finding (`tag == ...` in a filter)
+pub fn tags(finding: &Finding) -> Vec<&'static str> {
+    let mut tags = Vec::new();
+    if finding.generated {
+        tags.push("generated");
+    }
+    if !matches!(finding.fix_kind, None | Some(FixKind::Suggestion)) {
+        tags.push("fixable");
+    }
+    if finding.ai_false_positive {
+        tags.push("ai_false_positive");
+    }
+    tags
+}
+
+/// What a violated policy does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    /// Fail the review
+    Fail,
+    /// Report only
+    Warn,
+    /// Report that the change needs sign-off beyond the usual review
+    RequireEscalation,
+}
+
+impl Action {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "fail" => Some(Self::Fail),
+            "warn" => Some(Self::Warn),
+            "require-escalation" => Some(Self::RequireEscalation),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fail => "fail",
+            Self::Warn => "warn",
+            Self::RequireEscalation => "require-escalation",
+        }
+    }
+}
+
+/// How the matching findings are aggregated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Count,
+    Exists,
+}
+
+/// Comparison of the count with the threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparator {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl Comparator {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            ">" => Some(Self::Gt),
+            ">=" => Some(Self::Ge),
+            "<" => Some(Self::Lt),
+            "<=" => Some(Self::Le),
+            "==" => Some(Self::Eq),
+            "!=" => Some(Self::Ne),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+        }
+    }
+
+    fn holds(self, count: usize, threshold: usize) -> bool {
+        match self {
+            Self::Gt => count > threshold,
+            Self::Ge => count >= threshold,
+            Self::Lt => count < threshold,
+            Self::Le => count <= threshold,
+            Self::Eq => count == threshold,
+            Self::Ne => count != threshold,
+        }
+    }
+}
+
+/// A compiled `[[policy]]`
+#[derive(Debug, Clone)]
+pub struct Policy {
+    pub name: String,
+    pub description: Option<String>,
+    /// The filter as written
+    pub filter_source: String,
+    pub filter: Expr,
+    pub aggregate: Aggregate,
+    pub comparator: Comparator,
+    pub threshold: usize,
+    pub action: Action,
+}
+
+impl Policy {
+    /// Compile one policy; the error names the offending key
+    pub fn compile(config: &PolicyConfig) -> Result<Self, String> {
+        let filter = Expr::parse(&config.filter).map_err(|e| format!("filter: {}", e))?;
+        let aggregate = match config.aggregate.as_str() {
+            "count" => Aggregate::Count,
+            "exists" => Aggregate::Exists,
+            other => {
+                return Err(format!(
+                    "aggregate = {:?} is invalid. Must be: count, exists",
+                    other
+                ))
+            }
+        };
+        if aggregate == Aggregate::Exists
+            && (config.comparator.is_some() || config.threshold.is_some())
+        {
+            return Err("comparator and threshold only apply to aggregate = \"count\"".to_string());
+        }
+        let comparator = match config.comparator.as_deref() {
+            None => Comparator::Gt,
+            Some(c) => Comparator::parse(c).ok_or_else(|| {
+                format!(
+                    "comparator = {:?} is invalid. Must be: >, >=, <, <=, ==, !=",
+                    c
+                )
+            })?,
+        };
+        let action = Action::parse(&config.action).ok_or_else(|| {
+            format!(
+                "action = {:?} is invalid. Must be: fail, warn, require-escalation",
+                config.action
+            )
+        })?;
+        Ok(Self {
+            name: config.name.clone(),
+            description: config.description.clone(),
+            filter_source: config.filter.clone(),
+            filter,
+            aggregate,
+            comparator,
+            threshold: config.threshold.unwrap_or(0),
+            action,
+        })
+    }
+
+    /// Whether the policy also looks at baselined and suppressed findings
+    pub fn sees_filtered(&self) -> bool {
+        self.filter.mentions(Field::Status)
+    }
+
+    /// The violation condition, e.g. `count > 5` or `exists`
+    pub fn condition(&self) -> String {
+        match self.aggregate {
+            Aggregate::Count => format!("count {} {}", self.comparator.as_str(), self.threshold),
+            Aggregate::Exists => "exists".to_string(),
+        }
+    }
+
+    fn violated(&self, count: usize) -> bool {
+        match self.aggregate {
+            Aggregate::Count => self.comparator.holds(count, self.threshold),
+            Aggregate::Exists => count > 0,
+        }
+    }
+}
+
+/// Compile every policy, collecting `[[policy]]` errors for
+/// `validate-config`
+pub fn compile(configs: &[PolicyConfig]) -> Result<Vec<Policy>, Vec<String>> {
+    let mut policies = Vec::new();
+    let mut errors = Vec::new();
+    let mut names = HashSet::new();
+    for (i, config) in configs.iter().enumerate() {
+        let label = if config.name.is_empty() {
+            format!("policy[{}]", i)
+        } else {
+            format!("{:?}", config.name)
+        };
+        if config.name.is_empty() {
+            errors.push(format!("[[policy]] {}: name must not be empty", label));
+        } else if !names.insert(config.name.as_str()) {
+            errors.push(format!("[[policy]] {}: duplicate policy name", label));
+        }
+        match Policy::compile(config) {
+            Ok(policy) => policies.push(policy),
+            Err(e) => errors.push(format!("[[policy]] {}: {}", label, e)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(policies)
+    } else {
+        Err(errors)
+    }
+}
+
+/// A finding a policy matched
+#[derive(Debug, Clone)]
+pub struct PolicyMatch {
+    pub finding: Finding,
+    pub status: Status,
+}
+
+/// The outcome of one policy
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyResult {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub filter: String,
+    pub action: Action,
+    /// Violation condition, e.g. `count > 5`
+    pub condition: String,
+    /// Number of matching findings
+    pub count: usize,
+    pub passed: bool,
+    /// The matching findings (for `--explain-policy`)
+    #[serde(skip)]
+    pub matches: Vec<PolicyMatch>,
+}
+
+/// Results of all policies, in configuration order
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PolicyReport {
+    pub results: Vec<PolicyResult>,
+}
+
+impl PolicyReport {
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Violated policies with `action = "fail"`
+    pub fn failed(&self) -> impl Iterator<Item = &PolicyResult> {
+        self.violated(Action::Fail)
+    }
+
+    /// Violated policies with `action = "require-escalation"`
+    pub fn escalations(&self) -> impl Iterator<Item = &PolicyResult> {
+        self.violated(Action::RequireEscalation)
+    }
+
+    /// Whether a `fail` policy is violated
+    pub fn fails(&self) -> bool {
+        self.failed().next().is_some()
+    }
+
+    fn violated(&self, action: Action) -> impl Iterator<Item = &PolicyResult> {
+        self.results
+            .iter()
+            .filter(move |r| !r.passed && r.action == action)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PolicyResult> {
+        self.results.iter().find(|r| r.name == name)
+    }
+}
+
+/// Evaluate `policies` over the reported `findings` and the `suppressed`
+/// ones (baseline, inline and per-path)
+pub fn evaluate(
+    policies: &[Policy],
+    findings: &[Finding],
+    suppressed: &[SuppressedFinding],
+    repo_root: &Path,
+) -> PolicyReport {
+    let results = policies
+        .iter()
+        .map(|policy| {
+            let reported = findings
+                .iter()
+                .map(|f| Subject::new(f, repo_root, Status::New));
+            let filtered = suppressed
+                .iter()
+                .filter(|_| policy.sees_filtered())
+                .map(|s| Subject::new(&s.finding, repo_root, Status::of(s)));
+            let matches: Vec<PolicyMatch> = reported
+                .chain(filtered)
+                .filter(|subject| policy.filter.matches(subject))
+                .map(|subject| PolicyMatch {
+                    finding: subject.finding.clone(),
+                    status: subject.status,
+                })
+                .collect();
+            PolicyResult {
+                name: policy.name.clone(),
+                description: policy.description.clone(),
+                filter: policy.filter_source.clone(),
+                action: policy.action,
+                condition: policy.condition(),
+                count: matches.len(),
+                passed: !policy.violated(matches.len()),
+                matches,
+            }
+        })
+        .collect();
+    PolicyReport { results }
+}
@@ -0,0 +1,343 @@
+//! Named analysis profiles
+//!
+//! A profile bundles the settings that decide how deep a run goes: which
+//! files are scanned, whether the code graph is built, which modules run, the
+//! exit threshold and the confidence floor. Four are built in:
+//!
+//! | Profile     | Scope                          | Graph | Extras                                      |
+//! |-------------|--------------------------------|-------|---------------------------------------------|
+//! | `quick`     | changed files only             | no    | duplication off, AI off                     |
+//! | `standard`  | diff, full scan if none change | yes   | — (the default)                             |
+//! | `deep`      | whole repository               | yes   | duplication, dead code, history secret scan |
+//! | `first-run` | diff, full scan if none change | yes   | security, complexity and unused exports only, high confidence, 5 shown per rule |
+//!
+//! `first-run` is what a repository without any `.revet.toml` gets unless
+//! `--profile`, `--full` or `--module` is given.
+//!
+//! `[profiles.<name>]` sections define custom profiles, or adjust a built-in
+//! one, from the same keys. Commands resolve a [`ProfileSettings`] once, layer
+//! explicit flags on top (flags win, and each is recorded in `overrides`), then
+//! read every pipeline toggle from it.
+
+use crate::code_kind::CodeKind;
+use crate::config::{ModulesConfig, ProfileConfig, RevetConfig};
+use crate::finding::{Confidence, Finding, Severity};
+use crate::language_target::{LanguageTargets, TargetRecord};
+use crate::rollout::{Rollout, RolloutDecision};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+/// Built-in profile names
+pub const BUILTIN_PROFILES: &[&str] = &["quick", "standard", "deep", FIRST_RUN_PROFILE];
+
+/// Profile used when neither `--profile` nor `[general] profile` is set
+pub const DEFAULT_PROFILE: &str = "standard";
+
+/// Curated profile used when no `.revet.toml` exists
+pub const FIRST_RUN_PROFILE: &str = "first-run";
+
+/// Which files a run analyzes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    /// Changed files only; nothing is analyzed when nothing changed
+    Changed,
+    /// Changed files, falling back to a full scan when none are supported
+    Diff,
+    /// Every file in the repository
+    Full,
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Changed => write!(f, "changed"),
+            Self::Diff => write!(f, "diff"),
+            Self::Full => write!(f, "full"),
+        }
+    }
+}
+
+/// Effective settings for one run: a resolved profile plus explicit flags
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProfileSettings {
+    /// Profile name
+    pub name: String,
+    pub scope: Scope,
+    /// Base ref for diff-scoped runs
+    pub diff_base: String,
+    /// Build the code graph, run impact analysis and graph analyzers
+    pub graph: bool,
+    /// Run LLM reasoning
+    pub ai: bool,
+    /// Severity threshold for non-zero exit code
+    pub fail_on: String,
+    /// Findings below this confidence are dropped
+    pub min_confidence: Confidence,
+    /// Findings below this severity are dropped
+    pub min_severity: Severity,
+    /// Cyclomatic complexity threshold, over `[modules] complexity_threshold`
+    pub complexity_threshold: Option<usize>,
+    /// Findings displayed per rule; the rest only count in the summary
+    pub max_per_rule: Option<usize>,
+    /// Only findings in this kind of code are kept (`--only`)
+    pub only: Option<CodeKind>,
+    /// Scan git history for secrets no longer in HEAD
+    pub history_secrets: bool,
+    /// Module switches applied on top of `[modules]`
+    pub modules: BTreeMap<String, bool>,
+    /// Extra ignore globs
+    pub exclude: Vec<String>,
+    /// Sample `rollout_percent` modules; off with `--no-rollout-sampling`
+    pub rollout_sampling: bool,
+    /// Explicit flags that overrode the profile, e.g. `--fail-on error`
+    pub overrides: Vec<String>,
+}
+
+/// How a run was configured, recorded alongside its findings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reproducibility {
+    pub revet_version: String,
+    pub profile: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<String>,
+    /// Sampling decisions of the modules with a `rollout_percent`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rollout: Vec<RolloutDecision>,
+    /// How each language's version target was resolved at the root
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub language_targets: Vec<TargetRecord>,
+}
+
+impl Reproducibility {
+    /// Also record `rollout`'s sampling decisions
+    pub fn with_rollout(mut self, rollout: &Rollout) -> Self {
+        self.rollout = rollout.decisions().to_vec();
+        self
+    }
+
+    /// Also record the language targets of the repository at `root`
+    pub fn with_language_targets(mut self, root: &Path, config: &RevetConfig) -> Self {
+        self.language_targets = LanguageTargets::new(root, &config.targets).records();
+        self
+    }
+}
+
+impl ProfileSettings {
+    /// Resolve `name` (or `[general] profile`, or `standard`) against the
+    /// built-in and configured profiles
+    pub fn resolve(name: Option<&str>, config: &RevetConfig) -> Result<Self> {
+        let name = name
+            .or(config.general.profile.as_deref())
+            .unwrap_or(DEFAULT_PROFILE);
+
+        let mut settings = Self {
+            name: name.to_string(),
+            scope: Scope::Diff,
+            diff_base: config.general.diff_base.clone(),
+            graph: true,
+            ai: false,
+            fail_on: config.general.fail_on.clone(),
+            min_confidence: Confidence::Low,
+            min_severity: Severity::Info,
+            complexity_threshold: None,
+            max_per_rule: None,
+            only: None,
+            history_secrets: false,
+            modules: BTreeMap::new(),
+            exclude: Vec::new(),
+            rollout_sampling: true,
+            overrides: Vec::new(),
+        };
+        let layers = layers(name, config)?;
+        for layer in layers.iter().rev() {
+            settings.merge(layer);
+        }
+
+        for module in settings.modules.keys() {
+            if !ModulesConfig::NAMES.contains(&module.replace('-', "_").as_str()) {
+                bail!("unknown module {:?}", module);
+            }
+        }
+        if !["error", "warning", "info", "never"].contains(&settings.fail_on.as_str()) {
+            bail!(
+                "fail_on = {:?} is invalid. Must be one of: error, warning, info, never",
+                settings.fail_on
+            );
+        }
+        if let Some(min_severity) = layers.iter().find_map(|l| l.min_severity.as_deref()) {
+            settings.min_severity = match min_severity {
+                "error" => Severity::Error,
+                "warning" => Severity::Warning,
+                "info" => Severity::Info,
+                _ => bail!(
+                    "min_severity = {:?} is invalid. Must be one of: error, warning, info",
+                    min_severity
+                ),
+            };
+        }
+        Ok(settings)
+    }
+
+    fn merge(&mut self, layer: &ProfileConfig) {
+        if let Some(scope) = layer.scope {
+            self.scope = scope;
+        }
+        if let Some(graph) = layer.graph {
+            self.graph = graph;
+        }
+        if let Some(ai) = layer.ai {
+            self.ai = ai;
+        }
+        if let Some(fail_on) = &layer.fail_on {
+            self.fail_on = fail_on.clone();
+        }
+        if let Some(min_confidence) = layer.min_confidence {
+            self.min_confidence = min_confidence;
+        }
+        if let Some(history_secrets) = layer.history_secrets {
+            self.history_secrets = history_secrets;
+        }
+        if let Some(threshold) = layer.complexity_threshold {
+            self.complexity_threshold = Some(threshold);
+        }
+        if let Some(max) = layer.max_per_rule {
+            self.max_per_rule = Some(max);
+        }
+        self.modules
+            .extend(layer.modules.iter().map(|(k, v)| (k.clone(), *v)));
+        self.exclude.extend(layer.exclude.iter().cloned());
+    }
+
+    /// Record an explicit flag that overrode the profile
+    pub fn record_override(&mut self, flag: impl Into<String>) {
+        self.overrides.push(flag.into());
+    }
+
+    /// Write module switches, excludes, the diff base and `fail_on` into `config`
+    /// so analyzers built from it see the profile
+    pub fn apply(&self, config: &mut RevetConfig) {
+        for (module, enabled) in &self.modules {
+            config.modules.set(module, *enabled);
+        }
+        for glob in &self.exclude {
+            if !config.ignore.paths.contains(glob) {
+                config.ignore.paths.push(glob.clone());
+            }
+        }
+        if let Some(threshold) = self.complexity_threshold {
+            config.modules.complexity_threshold = threshold;
+        }
+        config.general.diff_base = self.diff_base.clone();
+        config.general.fail_on = self.fail_on.clone();
+    }
+
+    /// Whether a finding clears the confidence and severity floors and is in
+    /// the code kind `--only` selects. Findings must be tagged by
+    /// [`CodeKinds::apply`](crate::CodeKinds::apply) first.
+    pub fn keeps(&self, finding: &Finding) -> bool {
+        finding.confidence >= self.min_confidence
+            && severity_rank(finding.severity) >= severity_rank(self.min_severity)
+            && self.only.is_none_or(|kind| finding.code_kind == kind)
+    }
+
+    pub fn reproducibility(&self) -> Reproducibility {
+        Reproducibility {
+            revet_version: crate::VERSION.to_string(),
+            profile: self.name.clone(),
+            overrides: self.overrides.clone(),
+            rollout: Vec::new(),
+            language_targets: Vec::new(),
+        }
+    }
+}
+
+/// Built-in profile definitions, relative to the `standard` baseline
+pub fn builtin(name: &str) -> Option<ProfileConfig> {
+    match name {
+        "quick" => Some(ProfileConfig {
+            scope: Some(Scope::Changed),
+            graph: Some(false),
+            ai: Some(false),
+            modules: BTreeMap::from([("duplication".to_string(), false)]),
+            ..Default::default()
+        }),
+        "standard" => Some(ProfileConfig::default()),
+        "deep" => Some(ProfileConfig {
+            scope: Some(Scope::Full),
+            graph: Some(true),
+            history_secrets: Some(true),
+            modules: BTreeMap::from([
+                ("cycles".to_string(), true),
+                ("dead_code".to_string(), true),
+                ("duplication".to_string(), true),
+            ]),
+            ..Default::default()
+        }),
+        FIRST_RUN_PROFILE => Some(ProfileConfig {
+            min_confidence: Some(Confidence::High),
+            min_severity: Some("warning".to_string()),
+            complexity_threshold: Some(20),
+            max_per_rule: Some(5),
+            modules: ModulesConfig::NAMES
+                .iter()
+                .map(|m| {
+                    let on = matches!(*m, "security" | "complexity" | "dead_code");
+                    (m.to_string(), on)
+                })
+                .collect(),
+            ..Default::default()
+        }),
+        _ => None,
+    }
+}
+
+fn severity_rank(s: Severity) -> u8 {
+    match s {
+        Severity::Error => 2,
+        Severity::Warning => 1,
+        Severity::Info => 0,
+    }
+}
+
+/// The inheritance chain of `name`, most specific first
+///
+/// A configured profile without `extends` inherits the built-in of the same
+/// name, or `standard`; `extends` naming the profile itself also refers to
+/// the built-in.
+fn layers(name: &str, config: &RevetConfig) -> Result<Vec<ProfileConfig>> {
+    let mut layers = Vec::new();
+    let mut seen: Vec<&str> = Vec::new();
+    let mut next = (name, true);
+    loop {
+        let (current, configured) = next;
+        match config.profiles.get(current).filter(|_| configured) {
+            Some(custom) => {
+                if seen.contains(&current) {
+                    bail!("profile {:?} inherits from itself", current);
+                }
+                seen.push(current);
+                layers.push(custom.clone());
+                next = match custom.extends.as_deref() {
+                    Some(parent) => (parent, parent != current),
+                    None if builtin(current).is_some() => (current, false),
+                    None => (DEFAULT_PROFILE, true),
+                };
+            }
+            None => match builtin(current) {
+                Some(profile) => {
+                    layers.push(profile);
+                    return Ok(layers);
+                }
+                None => bail!(
+                    "unknown profile {:?} (built-in: {})",
+                    current,
+                    BUILTIN_PROFILES.join(", ")
+                ),
+            },
+        }
+    }
+}
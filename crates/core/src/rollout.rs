@@ -0,0 +1,199 @@
+//! Gradual rollout — `[analyzers.<module>] rollout_percent`
+//!
+//! A module with a rollout percentage reports findings only in the
+//! repositories a stable hash samples: each repository gets a bucket in
+//! `0..100` per module, from its identity (the normalised `origin` URL, or
+//! the directory name without one) and the module name, and is included
+//! when the bucket is below the percentage. The same repository always
+//! gets the same decision, and raising the percentage only adds
+//! repositories — one included at 25% is still included at 50%.
+//!
+//! Repositories outside the sample run the module in shadow mode: its
+//! findings are computed and counted ([`ShadowModule`]) but not reported,
+//! baselined or gated on. `--no-rollout-sampling` makes every module active.
+
+use crate::config::{ModulesConfig, RevetConfig};
+use crate::finding::{Finding, Severity};
+use crate::suggestion;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Largest valid `rollout_percent`
+pub const MAX_PERCENT: u8 = 100;
+
+/// Whether one module reports findings in this repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RolloutDecision {
+    /// `[modules]` name
+    pub module: String,
+    /// `rollout_percent` of the module
+    pub percent: u8,
+    /// The repository's place in `0..100` for this module
+    pub bucket: u8,
+    /// Whether the bucket is below the percentage
+    pub included: bool,
+    /// Whether the module's findings are reported: included in the
+    /// sample, or sampling is off (`--no-rollout-sampling`)
+    pub active: bool,
+}
+
+/// Rollout decisions of every module with a `rollout_percent`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rollout {
+    decisions: Vec<RolloutDecision>,
+}
+
+impl Rollout {
+    /// Decide for the repository at `repo_root`; without `sampling`
+    /// (`--no-rollout-sampling`) every module is active, and the decisions
+    /// still record which ones the sample includes
+    pub fn resolve(config: &RevetConfig, repo_root: &Path, sampling: bool) -> Self {
+        Self::for_identity(config, &repo_identity(repo_root), sampling)
+    }
+
+    /// Decide for the repository known as `identity` (see
+    /// [`repo_identity`])
+    pub fn for_identity(config: &RevetConfig, identity: &str, sampling: bool) -> Self {
+        let decisions = ModulesConfig::NAMES
+            .iter()
+            .filter_map(|module| {
+                let percent = config.rule_selection(module).rollout_percent?;
+                let bucket = bucket(identity, module);
+                let included = bucket < percent;
+                Some(RolloutDecision {
+                    module: module.to_string(),
+                    percent,
+                    bucket,
+                    included,
+                    active: included || !sampling,
+                })
+            })
+            .collect();
+        Self { decisions }
+    }
+
+    pub fn decisions(&self) -> &[RolloutDecision] {
+        &self.decisions
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.decisions.is_empty()
+    }
+
+    /// Whether `module` runs in shadow mode here
+    pub fn is_shadow(&self, module: &str) -> bool {
+        self.decisions
+            .iter()
+            .any(|d| d.module == module && !d.active)
+    }
+
+    /// Count the findings of shadowed modules; `module_of` gives a
+    /// finding's `[modules]` name
+    pub fn summarize<'a>(
+        &self,
+        shadow: &[Finding],
+        module_of: impl Fn(&Finding) -> Option<&'a str>,
+    ) -> Vec<ShadowModule> {
+        let mut modules: Vec<ShadowModule> = self
+            .decisions
+            .iter()
+            .filter(|d| !d.active)
+            .map(|d| ShadowModule {
+                module: d.module.clone(),
+                percent: d.percent,
+                ..Default::default()
+            })
+            .collect();
+        for finding in shadow {
+            let Some(module) = module_of(finding) else {
+                continue;
+            };
+            let Some(entry) = modules.iter_mut().find(|m| m.module == module) else {
+                continue;
+            };
+            match finding.severity {
+                Severity::Error => entry.errors += 1,
+                Severity::Warning => entry.warnings += 1,
+                Severity::Info => entry.info += 1,
+            }
+            *entry.rules.entry(suggestion::rule_of(finding)).or_default() += 1;
+        }
+        modules
+    }
+}
+
+/// Findings a module in shadow mode computed but did not report
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ShadowModule {
+    pub module: String,
+    /// `rollout_percent` of the module
+    pub percent: u8,
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+    /// Findings per rule
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rules: BTreeMap<String, usize>,
+}
+
+impl ShadowModule {
+    pub fn total(&self) -> usize {
+        self.errors + self.warnings + self.info
+    }
+}
+
+/// Bucket in `0..100` of the repository known as `identity` for `module`
+pub fn bucket(identity: &str, module: &str) -> u8 {
+    let digest = Sha256::digest(format!("{}\n{}", identity, module).as_bytes());
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&digest[..8]);
+    (u64::from_be_bytes(prefix) % u64::from(MAX_PERCENT)) as u8
+}
+
+/// What identifies the repository at `repo_root` for sampling: its
+/// `origin` URL as `host/owner/name`, so HTTPS and SSH clones agree, or
+/// the directory name when it has no `origin` remote
+pub fn repo_identity(repo_root: &Path) -> String {
+    let absolute = std::fs::canonicalize(repo_root).unwrap_or_else(|_| repo_root.to_path_buf());
+    git2::Repository::open(&absolute)
+        .ok()
+        .and_then(|repo| {
+            let remote = repo.find_remote("origin").ok()?;
+            remote.url().map(normalize_remote)
+        })
+        .unwrap_or_else(|| {
+            absolute
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        })
+}
+
+/// `https://user@Host/Owner/Name.git` and `git@host:owner/name` both as
+/// `host/owner/name`
+pub fn normalize_remote(url: &str) -> String {
+    let url = url.trim();
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    // Credentials (`user:token@`) end at the last `@` before the path
+    let authority_end = rest.find('/').unwrap_or(rest.len());
+    let rest = match rest[..authority_end].rfind('@') {
+        Some(at) => &rest[at + 1..],
+        None => rest,
+    };
+    let (host, mut path) = match rest.find(['/', ':']) {
+        Some(i) => (&rest[..i], &rest[i + 1..]),
+        None => (rest, ""),
+    };
+    // `ssh://host:22/owner/name`: the port is not part of the identity
+    if let Some((port, after)) = path.split_once('/') {
+        if !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()) {
+            path = after;
+        }
+    }
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    format!("{}/{}", host, path).to_lowercase()
+}
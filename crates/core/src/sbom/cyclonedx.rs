@@ -0,0 +1,108 @@
+//! CycloneDX 1.5 JSON rendering
+//!
+//! Components are `library` entries keyed (`bom-ref`) by package URL. Owning
+//! projects, manifests and direct/transitive status are `revet:*` properties;
+//! matched advisories go in the top-level `vulnerabilities` array.
+
+use super::{manifest_str, Sbom};
+use serde_json::{json, Value};
+
+/// Severity values allowed by the CycloneDX rating schema
+const SEVERITIES: &[&str] = &["critical", "high", "medium", "low", "info", "none"];
+
+pub fn to_json(sbom: &Sbom) -> Value {
+    let mut metadata_properties = Vec::new();
+    if let Some(commit) = &sbom.commit {
+        metadata_properties.push(property("revet:commit", commit));
+    }
+
+    let mut doc = json!({
+        "$schema": "http://cyclonedx.org/schema/bom-1.5.schema.json",
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "serialNumber": format!("urn:uuid:{}", sbom.uuid()),
+        "version": 1,
+        "metadata": {
+            "timestamp": sbom.timestamp(),
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": "revet",
+                    "version": crate::VERSION,
+                }],
+            },
+            "component": {
+                "type": "application",
+                "bom-ref": format!("revet:root:{}", sbom.name),
+                "name": sbom.name,
+            },
+            "properties": metadata_properties,
+        },
+        "components": sbom.components.iter().map(component).collect::<Vec<_>>(),
+    });
+
+    if !sbom.vulnerabilities.is_empty() {
+        doc["vulnerabilities"] = sbom
+            .vulnerabilities
+            .iter()
+            .map(|v| {
+                let a = &v.advisory;
+                let mut entry = json!({
+                    "bom-ref": format!("revet:vuln:{}:{}", a.id, a.package),
+                    "id": a.id,
+                    "ratings": [{ "severity": severity(a.severity.as_deref()) }],
+                    "affects": v.affects.iter().map(|p| json!({ "ref": p })).collect::<Vec<_>>(),
+                });
+                if let Some(summary) = &a.summary {
+                    entry["description"] = json!(summary);
+                }
+                if let Some(url) = &a.url {
+                    entry["source"] = json!({ "url": url });
+                    entry["advisories"] = json!([{ "url": url }]);
+                }
+                entry
+            })
+            .collect();
+    }
+
+    doc
+}
+
+fn component(c: &super::Component) -> Value {
+    let mut properties = vec![property(
+        "revet:dependency",
+        if c.is_direct() {
+            "direct"
+        } else {
+            "transitive"
+        },
+    )];
+    for (project, usage) in &c.projects {
+        properties.push(property("revet:project", project));
+        properties.push(property("revet:manifest", &manifest_str(&usage.manifest)));
+    }
+
+    json!({
+        "type": "library",
+        "bom-ref": c.purl,
+        "name": c.name,
+        "version": c.version,
+        "purl": c.purl,
+        "scope": "required",
+        "properties": properties,
+    })
+}
+
+fn property(name: &str, value: &str) -> Value {
+    json!({ "name": name, "value": value })
+}
+
+fn severity(raw: Option<&str>) -> &'static str {
+    let raw = raw.unwrap_or("").to_ascii_lowercase();
+    let raw = if raw == "moderate" { "medium" } else { &raw };
+    SEVERITIES
+        .iter()
+        .find(|s| **s == raw)
+        .copied()
+        .unwrap_or("unknown")
+}
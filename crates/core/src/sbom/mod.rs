@@ -0,0 +1,283 @@
+//! Software bill of materials (SBOM) generation
+//!
+//! Builds a component list from the repository's lockfiles (see
+//! [`crate::lockfile`]) and renders it as CycloneDX 1.5 or SPDX 2.3 JSON.
+//! Components are merged across a monorepo's lockfiles by package URL, with
+//! every owning project recorded. Advisories from an offline advisory file are
+//! attached to the components they match.
+//!
+//! Output is deterministic: components and vulnerabilities are sorted, and the
+//! serial number and timestamp derive from the HEAD commit rather than the
+//! clock. Nothing requires network access.
+
+pub mod cyclonedx;
+pub mod spdx;
+
+use crate::config::RevetConfig;
+use crate::lockfile::{self, advisory::Advisory, Ecosystem, LockedPackage};
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// SBOM output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbomFormat {
+    /// CycloneDX 1.5 JSON
+    CycloneDx,
+    /// SPDX 2.3 JSON
+    SpdxJson,
+}
+
+/// A package merged across every lockfile that resolves it
+#[derive(Debug, Clone)]
+pub struct Component {
+    pub purl: String,
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    pub version: String,
+    /// Owning projects, keyed by project name
+    pub projects: BTreeMap<String, ProjectUse>,
+}
+
+/// How one project depends on a component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectUse {
+    /// Manifest declaring the project's dependencies (repo-relative)
+    pub manifest: PathBuf,
+    pub direct: bool,
+}
+
+impl Component {
+    /// Direct dependency of at least one project
+    pub fn is_direct(&self) -> bool {
+        self.projects.values().any(|u| u.direct)
+    }
+}
+
+/// An advisory together with the components it affects
+#[derive(Debug, Clone)]
+pub struct Vulnerability {
+    pub advisory: Advisory,
+    /// Package URLs of affected components, sorted
+    pub affects: Vec<String>,
+}
+
+/// A bill of materials ready to render
+#[derive(Debug, Clone)]
+pub struct Sbom {
+    /// Document / root project name
+    pub name: String,
+    /// HEAD commit the SBOM describes, if the repo is a git checkout
+    pub commit: Option<String>,
+    /// Creation time, seconds since the Unix epoch
+    pub created: i64,
+    /// Sorted by package URL
+    pub components: Vec<Component>,
+    /// Sorted by advisory id
+    pub vulnerabilities: Vec<Vulnerability>,
+}
+
+impl Sbom {
+    /// Merge `packages` into components and match `advisories` against them
+    pub fn new(
+        name: impl Into<String>,
+        commit: Option<String>,
+        created: i64,
+        packages: Vec<LockedPackage>,
+        advisories: &[Advisory],
+    ) -> Self {
+        let mut by_purl: BTreeMap<String, Component> = BTreeMap::new();
+        let mut vulnerable: BTreeMap<(String, String), Vulnerability> = BTreeMap::new();
+
+        for pkg in packages {
+            let purl = pkg.purl();
+            for advisory in advisories.iter().filter(|a| a.matches(&pkg)) {
+                let key = (advisory.id.clone(), advisory.package.clone());
+                let vuln = vulnerable.entry(key).or_insert_with(|| Vulnerability {
+                    advisory: advisory.clone(),
+                    affects: Vec::new(),
+                });
+                if !vuln.affects.contains(&purl) {
+                    vuln.affects.push(purl.clone());
+                }
+            }
+
+            let component = by_purl.entry(purl.clone()).or_insert_with(|| Component {
+                purl,
+                ecosystem: pkg.ecosystem,
+                name: pkg.ecosystem.normalize_name(&pkg.name),
+                version: pkg.version.clone(),
+                projects: BTreeMap::new(),
+            });
+            let usage = component
+                .projects
+                .entry(pkg.project)
+                .or_insert_with(|| ProjectUse {
+                    manifest: pkg.manifest,
+                    direct: false,
+                });
+            usage.direct |= pkg.direct;
+        }
+
+        let mut vulnerabilities: Vec<Vulnerability> = vulnerable.into_values().collect();
+        for vuln in &mut vulnerabilities {
+            vuln.affects.sort();
+        }
+
+        Self {
+            name: name.into(),
+            commit,
+            created,
+            components: by_purl.into_values().collect(),
+            vulnerabilities,
+        }
+    }
+
+    /// Collect lockfiles under `repo_root` and build the SBOM for HEAD.
+    ///
+    /// `advisories` overrides `[modules] dependency_advisories` from the
+    /// config; relative paths resolve against `repo_root`.
+    pub fn from_repo(
+        repo_root: &Path,
+        config: &RevetConfig,
+        advisories: Option<&Path>,
+    ) -> Result<Self> {
+        let repo_root = repo_root.canonicalize()?;
+        let packages = lockfile::collect(&repo_root, &config.ignore.paths)?;
+
+        let advisory_path = advisories
+            .map(Path::to_path_buf)
+            .or_else(|| {
+                config
+                    .modules
+                    .dependency_advisories
+                    .as_ref()
+                    .map(PathBuf::from)
+            })
+            .map(|p| {
+                if p.is_absolute() {
+                    p
+                } else {
+                    repo_root.join(p)
+                }
+            });
+        let advisories = match advisory_path {
+            Some(path) => lockfile::advisory::load_advisories(&path)?,
+            None => Vec::new(),
+        };
+
+        let name = repo_root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "root".to_string());
+        let (commit, commit_time) = head_commit(&repo_root).unzip();
+        let created = commit_time
+            .or_else(|| std::env::var("SOURCE_DATE_EPOCH").ok()?.parse().ok())
+            .unwrap_or(0);
+
+        Ok(Self::new(name, commit, created, packages, &advisories))
+    }
+
+    /// Owning project names, sorted
+    pub fn projects(&self) -> Vec<&str> {
+        let mut projects: Vec<&str> = self
+            .components
+            .iter()
+            .flat_map(|c| c.projects.keys().map(String::as_str))
+            .collect();
+        projects.sort_unstable();
+        projects.dedup();
+        projects
+    }
+
+    /// Stable RFC 4122 UUID for this document.
+    ///
+    /// Derived from the commit hash when known, otherwise from the component
+    /// list, so regenerating the SBOM for the same tree yields the same id.
+    pub fn uuid(&self) -> String {
+        let seed = match &self.commit {
+            Some(commit) => format!("{}:{}", self.name, commit),
+            None => {
+                let purls: Vec<&str> = self.components.iter().map(|c| c.purl.as_str()).collect();
+                format!("{}:{}", self.name, purls.join(","))
+            }
+        };
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&fnv1a(seed.as_bytes(), 0xcbf2_9ce4_8422_2325).to_be_bytes());
+        bytes[8..].copy_from_slice(&fnv1a(seed.as_bytes(), 0x6c62_272e_07bb_0142).to_be_bytes());
+        // Version 5 (name-based), RFC 4122 variant
+        bytes[6] = (bytes[6] & 0x0f) | 0x50;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+        let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+    }
+
+    /// Creation time as `YYYY-MM-DDThh:mm:ssZ`
+    pub fn timestamp(&self) -> String {
+        rfc3339(self.created)
+    }
+
+    /// Render as pretty-printed JSON in `format`
+    pub fn render(&self, format: SbomFormat) -> Result<String> {
+        let doc = match format {
+            SbomFormat::CycloneDx => cyclonedx::to_json(self),
+            SbomFormat::SpdxJson => spdx::to_json(self),
+        };
+        let mut out = serde_json::to_string_pretty(&doc)?;
+        out.push('\n');
+        Ok(out)
+    }
+}
+
+/// HEAD commit hash and commit time
+fn head_commit(repo_root: &Path) -> Option<(String, i64)> {
+    let repo = git2::Repository::open(repo_root).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some((commit.id().to_string(), commit.time().seconds()))
+}
+
+/// Manifest path with forward slashes, as written into SBOM documents
+fn manifest_str(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn fnv1a(data: &[u8], offset: u64) -> u64 {
+    data.iter().fold(offset, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Format Unix seconds as an RFC 3339 UTC timestamp
+pub(crate) fn rfc3339(secs: i64) -> String {
+    let days = secs.div_euclid(86_400);
+    let rem = secs.rem_euclid(86_400);
+
+    // Civil-from-days (H. Hinnant)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
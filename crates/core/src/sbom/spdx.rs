@@ -0,0 +1,125 @@
+//! SPDX 2.3 JSON rendering
+//!
+//! Each owning project is a package the document `DESCRIBES`; each component
+//! is a package the project `DEPENDS_ON`, with the relationship comment saying
+//! whether the dependency is direct or transitive. Package URLs are
+//! `PACKAGE-MANAGER` external refs and matched advisories are `SECURITY`
+//! `advisory` refs on the affected package.
+
+use super::{manifest_str, Sbom};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+pub fn to_json(sbom: &Sbom) -> Value {
+    let uuid = sbom.uuid();
+    let projects = sbom.projects();
+    let project_ids: HashMap<&str, String> = projects
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (*p, format!("SPDXRef-Project-{}", i + 1)))
+        .collect();
+
+    let mut packages = Vec::new();
+    let mut relationships = Vec::new();
+
+    for project in &projects {
+        let id = &project_ids[project];
+        packages.push(json!({
+            "SPDXID": id,
+            "name": project,
+            "downloadLocation": "NOASSERTION",
+            "filesAnalyzed": false,
+            "primaryPackagePurpose": "APPLICATION",
+        }));
+        relationships.push(json!({
+            "spdxElementId": "SPDXRef-DOCUMENT",
+            "relationshipType": "DESCRIBES",
+            "relatedSpdxElement": id,
+        }));
+    }
+
+    for (i, c) in sbom.components.iter().enumerate() {
+        let id = format!("SPDXRef-Package-{}", i + 1);
+
+        let mut refs = vec![json!({
+            "referenceCategory": "PACKAGE-MANAGER",
+            "referenceType": "purl",
+            "referenceLocator": c.purl,
+        })];
+        for vuln in sbom
+            .vulnerabilities
+            .iter()
+            .filter(|v| v.affects.contains(&c.purl))
+        {
+            let a = &vuln.advisory;
+            let mut reference = json!({
+                "referenceCategory": "SECURITY",
+                "referenceType": "advisory",
+                "referenceLocator": a.url.as_deref().unwrap_or(&a.id),
+            });
+            let comment = match &a.summary {
+                Some(summary) => format!("{}: {}", a.id, summary),
+                None => a.id.clone(),
+            };
+            reference["comment"] = json!(comment);
+            refs.push(reference);
+        }
+
+        let declared: Vec<String> = c
+            .projects
+            .iter()
+            .map(|(project, usage)| format!("{} ({})", manifest_str(&usage.manifest), project))
+            .collect();
+
+        packages.push(json!({
+            "SPDXID": id,
+            "name": c.name,
+            "versionInfo": c.version,
+            "downloadLocation": "NOASSERTION",
+            "filesAnalyzed": false,
+            "primaryPackagePurpose": "LIBRARY",
+            "sourceInfo": format!("declared in {}", declared.join(", ")),
+            "externalRefs": refs,
+        }));
+
+        for (project, usage) in &c.projects {
+            relationships.push(json!({
+                "spdxElementId": project_ids[project.as_str()],
+                "relationshipType": "DEPENDS_ON",
+                "relatedSpdxElement": id,
+                "comment": if usage.direct { "direct" } else { "transitive" },
+            }));
+        }
+    }
+
+    let mut doc = json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": sbom.name,
+        "documentNamespace": format!("https://spdx.org/spdxdocs/{}-{}", sanitize(&sbom.name), uuid),
+        "creationInfo": {
+            "created": sbom.timestamp(),
+            "creators": [format!("Tool: revet-{}", crate::VERSION)],
+        },
+        "packages": packages,
+        "relationships": relationships,
+    });
+    if let Some(commit) = &sbom.commit {
+        doc["comment"] = json!(format!("Generated from commit {}", commit));
+    }
+    doc
+}
+
+/// Restrict to characters valid in a URI path segment
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,407 @@
+//! Budget-aware scheduling of the review pipeline (`--time-budget`)
+//!
+//! A review runs in [`Stage`]s: the content analyzers, the code graph build,
+//! the graph analyzers, then the whole-repository scans (duplication,
+//! hotspots, ownership, git history secrets). With a time budget, the [`Scheduler`]
+//! admits each stage's [`Phase`]s cheapest first and skips a phase whose
+//! estimate no longer fits the time left, so the run ends on time with
+//! partial coverage instead of blowing its slot.
+//!
+//! Estimates come from how long the phase took the last time it ran
+//! ([`Timings`], kept in the state directory), scaled by the number of
+//! files. A phase with no stored timing gets a conservative static estimate.
+//! Without a budget every phase is admitted and the scheduler only records
+//! timings for the next run.
+//!
+//! The scheduler sits above the [`AnalyzerDispatcher`](crate::AnalyzerDispatcher):
+//! analyzers are skipped by switching their `[modules]` entry off for the
+//! run, so they know nothing about budgets.
+
+use crate::config::RevetConfig;
+use crate::storage::{StoragePaths, TIMINGS_FILE};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Phase key of the code graph build
+pub const GRAPH: &str = "graph";
+/// Phase key of the git history secret scan
+pub const HISTORY: &str = "history";
+
+/// Content modules that scan the whole repository at once; a budgeted run
+/// defers them to [`Stage::Scans`]
+const SCAN_MODULES: &[&str] = &["duplication"];
+
+/// Phases whose cost follows the git history rather than the file count
+const UNSCALED: &[&str] = &[HISTORY, "hotspots", "ownership"];
+
+/// Parts of a review, in the order a budgeted run goes through them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    /// Content analyzers on the analyzed files
+    Content,
+    /// Building the code graph
+    Graph,
+    /// Graph analyzers and refactor verification
+    GraphAnalyzers,
+    /// Duplication, hotspots, ownership and the git history secret scan
+    Scans,
+}
+
+/// One schedulable unit of work: a `[modules]` entry, or [`GRAPH`] or
+/// [`HISTORY`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Phase {
+    pub key: String,
+    pub stage: Stage,
+}
+
+impl Phase {
+    pub fn new(key: impl Into<String>, stage: Stage) -> Self {
+        Self {
+            key: key.into(),
+            stage,
+        }
+    }
+
+    /// How the phase is named in the summary
+    pub fn label(&self) -> String {
+        match self.key.as_str() {
+            GRAPH => "code graph".to_string(),
+            HISTORY => "git history secret scan".to_string(),
+            "hotspots" => "hotspot ranking".to_string(),
+            "ownership" => "ownership analysis".to_string(),
+            "refactor" => "refactor verification".to_string(),
+            module => format!("{} analyzer", module.replace('_', " ")),
+        }
+    }
+
+    /// First-run estimate, deliberately on the slow side
+    fn static_estimate(&self, files: usize) -> f64 {
+        let files = files as f64;
+        match (self.stage, self.key.as_str()) {
+            (_, HISTORY) => 60.0,
+            (_, "hotspots") => 20.0,
+            (_, "ownership") => 60.0,
+            (Stage::Content, _) => 0.005 * files,
+            (Stage::GraphAnalyzers, "refactor") => 0.02 * files,
+            (Stage::GraphAnalyzers, _) => 0.005 * files,
+            (Stage::Graph | Stage::Scans, _) => 0.02 * files,
+        }
+    }
+}
+
+/// How long a phase took the last time it ran
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub secs: f64,
+    /// Files the run analyzed
+    pub files: usize,
+}
+
+/// Stored phase timings, by phase key
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    pub phases: BTreeMap<String, PhaseTiming>,
+    /// Most bytes each `[modules]` entry had allocated at once, from
+    /// `alloc-stats` builds; ranks analyzers when memory runs short
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub peaks: BTreeMap<String, u64>,
+}
+
+impl Timings {
+    /// The timings saved by the last run; `None` when there are none or
+    /// they can't be read
+    pub fn load(paths: &StoragePaths) -> Option<Self> {
+        let bytes = std::fs::read(paths.read_path(TIMINGS_FILE)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Write the timings to the state directory
+    pub fn save(&self, paths: &StoragePaths) -> Result<()> {
+        paths.ensure_data_dir()?;
+        let path = paths.write_path(TIMINGS_FILE);
+        let json = serde_json::to_vec_pretty(self)?;
+        std::fs::write(&path, json).with_context(|| format!("write {}", path.display()))
+    }
+}
+
+/// A phase the budget left out
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SkippedPhase {
+    pub phase: String,
+    pub estimated_secs: f64,
+    /// Budget left when the phase was up
+    pub remaining_secs: f64,
+    /// The skipped phase this one depends on, when that is why it was
+    /// skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub needs: Option<String>,
+}
+
+impl fmt::Display for SkippedPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.needs {
+            Some(needs) => write!(f, "{}, needs the {}", self.phase, needs),
+            None => write!(
+                f,
+                "{}, estimated {}, {} remaining",
+                self.phase,
+                secs(self.estimated_secs),
+                secs(self.remaining_secs)
+            ),
+        }
+    }
+}
+
+/// `190s`, or `0.4s` under ten seconds
+fn secs(secs: f64) -> String {
+    if secs < 10.0 {
+        format!("{:.1}s", secs)
+    } else {
+        format!("{:.0}s", secs)
+    }
+}
+
+/// How a budgeted run went
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BudgetReport {
+    /// Whether a phase was skipped or the run took longer than the budget:
+    /// the findings then cover only part of what was configured
+    pub budget_exceeded: bool,
+    pub time_budget_secs: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_phases: Vec<SkippedPhase>,
+}
+
+/// Admits phases against the time left in the budget and records how long
+/// they took
+#[derive(Debug)]
+pub struct Scheduler {
+    budget: Option<Duration>,
+    start: Instant,
+    /// Files the run analyzes, for scaling stored timings
+    files: usize,
+    previous: Timings,
+    measured: BTreeMap<String, f64>,
+    peaks: BTreeMap<String, u64>,
+    skipped: Vec<SkippedPhase>,
+}
+
+impl Scheduler {
+    /// A scheduler for a run over `files` files that started now.
+    /// `previous` are the stored timings, if any.
+    pub fn new(budget: Option<Duration>, previous: Option<Timings>, files: usize) -> Self {
+        Self {
+            budget,
+            start: Instant::now(),
+            files,
+            previous: previous.unwrap_or_default(),
+            measured: BTreeMap::new(),
+            peaks: BTreeMap::new(),
+            skipped: Vec::new(),
+        }
+    }
+
+    pub fn is_budgeted(&self) -> bool {
+        self.budget.is_some()
+    }
+
+    /// Budget left, `None` without a budget
+    pub fn remaining(&self) -> Option<Duration> {
+        self.budget
+            .map(|budget| budget.saturating_sub(self.start.elapsed()))
+    }
+
+    /// Expected duration of `phase`: its stored timing scaled to this run's
+    /// file count, else the static estimate
+    pub fn estimate(&self, phase: &Phase) -> Duration {
+        let secs = match self.previous.phases.get(&phase.key) {
+            Some(timing) if timing.files > 0 && !UNSCALED.contains(&phase.key.as_str()) => {
+                timing.secs * self.files as f64 / timing.files as f64
+            }
+            Some(timing) => timing.secs,
+            None => phase.static_estimate(self.files),
+        };
+        Duration::from_secs_f64(secs.max(0.0))
+    }
+
+    /// Whether `phase` fits the budget left; records it as skipped if not
+    pub fn admit(&mut self, phase: &Phase) -> bool {
+        !self.admit_all(vec![phase.clone()], false).is_empty()
+    }
+
+    /// Admit `phases` as one batch, cheapest first, until the next one no
+    /// longer fits. A `parallel` batch is expected to take as long as its
+    /// slowest phase or its total spread over the worker threads, whichever
+    /// is longer; otherwise the phases add up.
+    pub fn admit_all(&mut self, mut phases: Vec<Phase>, parallel: bool) -> Vec<Phase> {
+        let Some(remaining) = self.remaining() else {
+            return phases;
+        };
+        phases.sort_by_key(|phase| self.estimate(phase));
+        let threads = rayon::current_num_threads().max(1) as u32;
+
+        let mut admitted = Vec::new();
+        let (mut total, mut longest, mut cost) = (Duration::ZERO, Duration::ZERO, Duration::ZERO);
+        for phase in phases {
+            let estimate = self.estimate(&phase);
+            let next_total = total + estimate;
+            let next_longest = longest.max(estimate);
+            let next_cost = if parallel {
+                next_longest.max(next_total / threads)
+            } else {
+                next_total
+            };
+            if next_cost <= remaining {
+                (total, longest, cost) = (next_total, next_longest, next_cost);
+                admitted.push(phase);
+            } else {
+                self.skipped.push(SkippedPhase {
+                    phase: phase.label(),
+                    estimated_secs: estimate.as_secs_f64(),
+                    remaining_secs: remaining.saturating_sub(cost).as_secs_f64(),
+                    needs: None,
+                });
+            }
+        }
+        admitted
+    }
+
+    /// Admit the content analyzers of `modules` (`[modules]` names) as one
+    /// parallel batch. Returns `config` with the skipped modules switched
+    /// off, and the whole-repository scans among `modules`, which a
+    /// budgeted run defers to [`Stage::Scans`] and leaves off too.
+    pub fn admit_content<'a>(
+        &mut self,
+        modules: &[&'a str],
+        config: &RevetConfig,
+    ) -> (RevetConfig, Vec<&'a str>) {
+        let (scans, now): (Vec<&str>, Vec<&str>) = modules
+            .iter()
+            .partition(|m| self.is_budgeted() && SCAN_MODULES.contains(m));
+        let phases = now.iter().map(|m| Phase::new(*m, Stage::Content)).collect();
+        let admitted = self.admit_all(phases, true);
+        let mut config = config.clone();
+        for module in modules {
+            if scans.contains(module) || !admitted.iter().any(|p| p.key == *module) {
+                config.modules.set(module, false);
+            }
+        }
+        (config, scans)
+    }
+
+    /// Admit the graph analyzers of `modules` (`[modules]` names), which run
+    /// one after another. Returns `config` with the skipped ones switched
+    /// off.
+    pub fn admit_graph(&mut self, modules: &[&str], config: &RevetConfig) -> RevetConfig {
+        let phases = modules
+            .iter()
+            .map(|m| Phase::new(*m, Stage::GraphAnalyzers))
+            .collect();
+        let admitted = self.admit_all(phases, false);
+        let mut config = config.clone();
+        for module in modules {
+            if !admitted.iter().any(|p| p.key == *module) {
+                config.modules.set(module, false);
+            }
+        }
+        config
+    }
+
+    /// Record `phase` as skipped because `needs`, which it depends on, was
+    pub fn skip_dependent(&mut self, phase: &Phase, needs: &Phase) {
+        self.skipped.push(SkippedPhase {
+            phase: phase.label(),
+            estimated_secs: self.estimate(phase).as_secs_f64(),
+            remaining_secs: self.remaining().unwrap_or_default().as_secs_f64(),
+            needs: Some(needs.label()),
+        });
+    }
+
+    /// Record that the phase `key` ran for `took`
+    pub fn record(&mut self, key: &str, took: Duration) {
+        *self.measured.entry(key.to_string()).or_default() += took.as_secs_f64();
+    }
+
+    /// Record that the phase `key` had at most `bytes` allocated at once
+    pub fn record_peak(&mut self, key: &str, bytes: u64) {
+        let peak = self.peaks.entry(key.to_string()).or_default();
+        *peak = (*peak).max(bytes);
+    }
+
+    /// Peak allocation of the phase `key`: this run's, else the stored one
+    pub fn peak(&self, key: &str) -> Option<u64> {
+        self.peaks
+            .get(key)
+            .or_else(|| self.previous.peaks.get(key))
+            .copied()
+    }
+
+    /// Whether the budget left any phase out
+    pub fn skipped_any(&self) -> bool {
+        !self.skipped.is_empty()
+    }
+
+    /// The phases left out so far
+    pub fn skipped(&self) -> &[SkippedPhase] {
+        &self.skipped
+    }
+
+    /// How the run fared against its budget; `None` without a budget
+    pub fn report(&self) -> Option<BudgetReport> {
+        let budget = self.budget?;
+        Some(BudgetReport {
+            budget_exceeded: self.skipped_any() || self.start.elapsed() > budget,
+            time_budget_secs: budget.as_secs_f64(),
+            skipped_phases: self.skipped.clone(),
+        })
+    }
+
+    /// The stored timings updated with the phases this run measured
+    pub fn timings(&self) -> Timings {
+        let mut timings = self.previous.clone();
+        timings.peaks.extend(self.peaks.clone());
+        for (key, secs) in &self.measured {
+            timings.phases.insert(
+                key.clone(),
+                PhaseTiming {
+                    secs: *secs,
+                    files: self.files,
+                },
+            );
+        }
+        timings
+    }
+}
+
+/// Parse a `--time-budget` value: seconds, or a number with an `s`, `m` or
+/// `h` suffix (`300`, `300s`, `5m`, `1.5h`)
+pub fn parse_budget(value: &str) -> std::result::Result<Duration, String> {
+    let value = value.trim();
+    let invalid = || {
+        format!(
+            "invalid time budget {:?}: expected seconds or a number with s, m or h, e.g. 300s or 5m",
+            value
+        )
+    };
+    let (number, unit) = match value.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(i) => value.split_at(i),
+        None => (value, "s"),
+    };
+    let scale = match unit {
+        "s" => 1.0,
+        "m" => 60.0,
+        "h" => 3600.0,
+        _ => return Err(invalid()),
+    };
+    let number: f64 = number.trim().parse().map_err(|_| invalid())?;
+    if !number.is_finite() || number < 0.0 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs_f64(number * scale))
+}
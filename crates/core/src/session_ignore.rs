@@ -0,0 +1,260 @@
+//! Session ignores — findings hidden on one machine for a few hours with
+//! `revet ignore`, without touching the baseline, the source or the config
+//!
+//! An entry names either one finding, by the baseline's match key (file,
+//! redacted message, rule and source, see [`BaselineEntry`]), or a rule
+//! and/or path glob, matched like `revet-ignore` tokens and `[ignore]
+//! per_path` globs. Every entry expires after its TTL.
+//!
+//! Entries are kept in [`SESSION_IGNORES_FILE`] under
+//! [`StoragePaths::local_dir`], never in the repository. Reviews apply them
+//! last, after inline suppression and the baseline, and count what they hid
+//! in the summary. They never apply in CI (see [`in_ci`]).
+
+use crate::baseline::{Baseline, BaselineEntry};
+use crate::storage::{StoragePaths, SESSION_IGNORES_FILE};
+use crate::suppress::{matches_finding, SuppressedFinding};
+use crate::Finding;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How long an entry lasts without `--ttl`
+pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 3600);
+
+/// Suppression reason of the findings a session ignore hid
+pub const REASON: &str = "session";
+
+/// Variables set by CI services: `CI` by most of them, the others by those
+/// that don't
+const CI_ENV: &[&str] = &[
+    "CI",
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "BUILDKITE",
+    "CIRCLECI",
+    "JENKINS_URL",
+    "TF_BUILD",
+    "TEAMCITY_VERSION",
+];
+
+/// Whether the process runs in a CI environment, where session ignores
+/// never apply
+pub fn in_ci() -> bool {
+    CI_ENV.iter().any(|name| {
+        std::env::var(name)
+            .is_ok_and(|v| !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false"))
+    })
+}
+
+/// Seconds since the Unix epoch
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Parse a `--ttl` value: a number with an `s`, `m` or `h` suffix (`30m`,
+/// `8h`)
+pub fn parse_ttl(value: &str) -> std::result::Result<Duration, String> {
+    match crate::schedule::parse_budget(value) {
+        Ok(ttl) if !ttl.is_zero() => Ok(ttl),
+        _ => Err(format!(
+            "invalid TTL {:?}: expected a number with s, m or h, e.g. 30m or 8h",
+            value
+        )),
+    }
+}
+
+/// One `revet ignore add`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionIgnore {
+    /// Finding ID the entry was added for, as shown by the run it came from.
+    /// For display only: IDs are renumbered each run, `finding` matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Match key of that finding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finding: Option<BaselineEntry>,
+    /// Prefix, module or `module:rule`, as in a `revet-ignore` comment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    /// Glob over paths relative to the repository root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// When the entry was added, in Unix seconds
+    pub added: u64,
+    /// When the entry stops applying, in Unix seconds
+    pub expires: u64,
+}
+
+impl SessionIgnore {
+    /// Entry hiding `finding`, reported as `id`, for `ttl` from `now`
+    pub fn finding(id: &str, finding: &Finding, repo_root: &Path, now: u64, ttl: Duration) -> Self {
+        Self {
+            id: Some(id.to_string()),
+            finding: Some(BaselineEntry::of(finding, repo_root)),
+            rule: None,
+            path: None,
+            added: now,
+            expires: now.saturating_add(ttl.as_secs()),
+        }
+    }
+
+    /// Entry hiding every finding of `rule` under the `path` glob (either
+    /// may be left out, not both), for `ttl` from `now`
+    pub fn rule(rule: Option<&str>, path: Option<&str>, now: u64, ttl: Duration) -> Result<Self> {
+        if rule.is_none() && path.is_none() {
+            return Err(anyhow!(
+                "a session ignore needs a finding ID, a rule or a path"
+            ));
+        }
+        if let Some(glob) = path {
+            glob::Pattern::new(glob).with_context(|| format!("invalid path glob {:?}", glob))?;
+        }
+        Ok(Self {
+            id: None,
+            finding: None,
+            rule: rule.map(str::to_string),
+            path: path.map(str::to_string),
+            added: now,
+            expires: now.saturating_add(ttl.as_secs()),
+        })
+    }
+
+    /// Time left at `now`; `None` once expired
+    pub fn remaining(&self, now: u64) -> Option<Duration> {
+        (self.expires > now).then(|| Duration::from_secs(self.expires - now))
+    }
+
+    /// What the entry hides, for `revet ignore list`
+    pub fn describe(&self) -> String {
+        if let Some(entry) = &self.finding {
+            let id = self.id.as_deref().unwrap_or("finding");
+            return format!("{} in {}: {}", id, entry.file, entry.message);
+        }
+        match (&self.rule, &self.path) {
+            (Some(rule), Some(path)) => format!("rule {} under {}", rule, path),
+            (Some(rule), None) => format!("rule {}", rule),
+            (None, Some(path)) => format!("everything under {}", path),
+            (None, None) => "nothing".to_string(),
+        }
+    }
+
+    /// Whether this rule/path entry covers `finding`, whose repository
+    /// relative path is `rel`
+    fn covers(&self, finding: &Finding, rel: &str) -> bool {
+        if self.finding.is_some() {
+            return false;
+        }
+        let rule = self
+            .rule
+            .as_ref()
+            .is_none_or(|rule| matches_finding(finding, std::slice::from_ref(rule)));
+        let path = self.path.as_deref().is_none_or(|glob| {
+            !finding.is_repository_level()
+                && glob::Pattern::new(glob).is_ok_and(|pattern| pattern.matches(rel))
+        });
+        (self.rule.is_some() || self.path.is_some()) && rule && path
+    }
+}
+
+/// The session ignores of one repository
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionIgnores {
+    pub entries: Vec<SessionIgnore>,
+}
+
+impl SessionIgnores {
+    /// Where the entries of `paths` are kept; `None` when there is no local
+    /// directory (see [`StoragePaths::local_dir`])
+    pub fn path(paths: &StoragePaths) -> Option<PathBuf> {
+        Some(paths.local_dir()?.join(SESSION_IGNORES_FILE))
+    }
+
+    /// Load the entries at `path`; none when the file doesn't exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        serde_json::from_str(&data).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    /// Save the entries to `path`, removing the file when there are none
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if self.entries.is_empty() {
+            return match std::fs::remove_file(path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    Err(e).with_context(|| format!("removing {}", path.display()))
+                }
+                _ => Ok(()),
+            };
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self).context("serializing session ignores")?;
+        std::fs::write(path, json).with_context(|| format!("writing {}", path.display()))
+    }
+
+    /// Drop the entries expired at `now`; returns how many went
+    pub fn prune(&mut self, now: u64) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.remaining(now).is_some());
+        before - self.entries.len()
+    }
+
+    /// Whether any entry still applies at `now`
+    pub fn is_active(&self, now: u64) -> bool {
+        self.entries.iter().any(|e| e.remaining(now).is_some())
+    }
+
+    /// Split `findings` into those no entry unexpired at `now` covers and
+    /// those one does, suppressed with reason [`REASON`]
+    pub fn apply(
+        &self,
+        findings: Vec<Finding>,
+        repo_root: &Path,
+        now: u64,
+    ) -> (Vec<Finding>, Vec<SuppressedFinding>) {
+        let active: Vec<&SessionIgnore> = self
+            .entries
+            .iter()
+            .filter(|e| e.remaining(now).is_some())
+            .collect();
+        // Single findings match like baseline entries do
+        let pinned = Baseline {
+            version: "2".to_string(),
+            created_at: String::new(),
+            commit: None,
+            count: 0,
+            entries: active.iter().filter_map(|e| e.finding.clone()).collect(),
+        };
+        let known = pinned.known(&findings, repo_root);
+
+        let mut kept = Vec::new();
+        let mut ignored = Vec::new();
+        for (finding, known) in findings.into_iter().zip(known) {
+            let rel = finding
+                .file
+                .strip_prefix(repo_root)
+                .unwrap_or(&finding.file)
+                .to_string_lossy()
+                .into_owned();
+            if known || active.iter().any(|e| e.covers(&finding, &rel)) {
+                ignored.push(SuppressedFinding {
+                    finding,
+                    reason: REASON.to_string(),
+                });
+            } else {
+                kept.push(finding);
+            }
+        }
+        (kept, ignored)
+    }
+}
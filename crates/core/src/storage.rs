@@ -0,0 +1,297 @@
+//! Where revet keeps its state
+//!
+//! Everything revet writes — the graph cache, per-file parse cache, graph
+//! store, run logs, the last run's findings, churn cache, phase timings,
+//! baselines and the audit log — goes through [`StoragePaths`].
+//! By default that is `.revet-cache/` inside the repository. With
+//! `REVET_DATA_DIR` or `[storage] data_dir` set, each repository gets its own
+//! directory under it instead, named after the repository and a hash of its
+//! `origin` URL (or of its absolute path when there is no remote), so a
+//! read-only checkout can be reviewed.
+//!
+//! Reads fall back to the legacy in-repo location while the new one has
+//! nothing yet; [`StoragePaths::migrate`] moves existing state over.
+//!
+//! Session ignores (`revet ignore`) are personal and short-lived, so they
+//! never go to `.revet-cache/`: [`StoragePaths::local_dir`] puts them in the
+//! per-user state directory unless the data directory is relocated.
+
+use crate::config::RevetConfig;
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding `[storage] data_dir`
+pub const DATA_DIR_ENV: &str = "REVET_DATA_DIR";
+
+/// State directory inside the repository when no data directory is set
+pub const LEGACY_DIR: &str = ".revet-cache";
+
+/// Graph store database
+pub const GRAPH_DB: &str = "graph.db";
+/// Per-file parse cache directory
+pub const FILES_DIR: &str = "files";
+/// Cached domain analyzer findings, kept while a remote cache is configured
+pub const ANALYSIS_DIR: &str = "analysis";
+/// Run log directory
+pub const RUNS_DIR: &str = "runs";
+/// Every finding of the last review, with its snippet, for `revet explain`
+pub const LAST_RUN_FILE: &str = "last-run.json";
+/// Churn cache for the hotspots analyzer
+pub const CHURN_FILE: &str = "churn.json";
+/// Blame cache for the ownership analyzer
+pub const AUTHORSHIP_FILE: &str = "authorship.json";
+/// Phase timings of the last run, for `--time-budget` estimates
+pub const TIMINGS_FILE: &str = "timings.json";
+/// Default baseline file name
+pub const BASELINE_FILE: &str = "baseline.json";
+/// Per-change baselines, one `<Change-Id>.json` each
+pub const CHANGES_DIR: &str = "changes";
+/// Audit log of the changes revet made to the working tree
+pub const AUDIT_FILE: &str = "audit.log.jsonl";
+/// Sequence number and hash of the audit log's last entry
+pub const AUDIT_HEAD_FILE: &str = "audit.head";
+/// Commands that changed files with the audit log off
+pub const AUDIT_GAP_FILE: &str = "audit.gap";
+/// Findings hidden for a while with `revet ignore`, in the local directory
+pub const SESSION_IGNORES_FILE: &str = "session-ignores.json";
+/// Socket of `revet daemon`, in the local directory
+pub const DAEMON_SOCKET: &str = "daemon.sock";
+
+/// Per-user state directory of [`StoragePaths::local_dir`]
+/// (`~/.local/state` when unset)
+pub const STATE_HOME_ENV: &str = "XDG_STATE_HOME";
+
+/// Resolved state locations for one repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoragePaths {
+    data_dir: PathBuf,
+    legacy_dir: PathBuf,
+    baseline: PathBuf,
+}
+
+/// What [`StoragePaths::migrate`] did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrateReport {
+    /// Entries moved, by name in the legacy directory
+    pub moved: Vec<String>,
+    /// Entries left in place because the destination already exists
+    pub skipped: Vec<String>,
+}
+
+impl StoragePaths {
+    /// The default layout: everything in `<repo_root>/.revet-cache`
+    pub fn in_repo(repo_root: &Path) -> Self {
+        let legacy_dir = repo_root.join(LEGACY_DIR);
+        Self {
+            baseline: legacy_dir.join(BASELINE_FILE),
+            data_dir: legacy_dir.clone(),
+            legacy_dir,
+        }
+    }
+
+    /// Locations for `repo_root` under `config`, with `REVET_DATA_DIR`
+    /// taking precedence over `[storage] data_dir`
+    pub fn resolve(repo_root: &Path, config: &RevetConfig) -> Self {
+        let env = std::env::var(DATA_DIR_ENV).ok().filter(|v| !v.is_empty());
+        Self::with_data_dir(
+            repo_root,
+            env.as_deref().or(config.storage.data_dir.as_deref()),
+            config.baseline.path.as_deref(),
+        )
+    }
+
+    /// [`resolve`](Self::resolve) with the configuration found for
+    /// `repo_root` (defaults when there is none or it doesn't load)
+    pub fn discover(repo_root: &Path) -> Self {
+        let config = RevetConfig::find_and_load(repo_root).unwrap_or_default();
+        Self::resolve(repo_root, &config)
+    }
+
+    /// Locations with an explicit data directory and baseline path.
+    /// Relative paths are resolved against `repo_root`.
+    pub fn with_data_dir(repo_root: &Path, data_dir: Option<&str>, baseline: Option<&str>) -> Self {
+        let mut paths = Self::in_repo(repo_root);
+        if let Some(dir) = data_dir {
+            paths.data_dir = repo_root.join(dir).join(repo_key(repo_root));
+            paths.baseline = paths.data_dir.join(BASELINE_FILE);
+        }
+        if let Some(path) = baseline {
+            paths.baseline = repo_root.join(path);
+        }
+        paths
+    }
+
+    /// Directory state is written to
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// `<repo_root>/.revet-cache`, read when the data directory lacks an entry
+    pub fn legacy_dir(&self) -> &Path {
+        &self.legacy_dir
+    }
+
+    /// Whether state is kept outside `.revet-cache/`
+    pub fn is_relocated(&self) -> bool {
+        self.data_dir != self.legacy_dir
+    }
+
+    /// Where to write `name`
+    pub fn write_path(&self, name: &str) -> PathBuf {
+        self.data_dir.join(name)
+    }
+
+    /// Directory for state that belongs to this user and machine and must
+    /// never land in the repository: the data directory when it is
+    /// relocated, else `revet/<repo key>` under `$XDG_STATE_HOME`
+    /// (`~/.local/state`). `None` without a home directory.
+    pub fn local_dir(&self) -> Option<PathBuf> {
+        if self.is_relocated() {
+            return Some(self.data_dir.clone());
+        }
+        let state_home = std::env::var_os(STATE_HOME_ENV)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                let home = std::env::var_os("HOME").filter(|v| !v.is_empty())?;
+                Some(Path::new(&home).join(".local").join("state"))
+            })?;
+        let repo_root = self.legacy_dir.parent()?;
+        Some(state_home.join("revet").join(repo_key(repo_root)))
+    }
+
+    /// Where to read `name` from: the data directory, else the legacy
+    /// directory when only it has the entry
+    pub fn read_path(&self, name: &str) -> PathBuf {
+        let path = self.data_dir.join(name);
+        if !path.exists() && self.is_relocated() {
+            let legacy = self.legacy_dir.join(name);
+            if legacy.exists() {
+                return legacy;
+            }
+        }
+        path
+    }
+
+    /// Where to write the baseline
+    pub fn baseline_path(&self) -> &Path {
+        &self.baseline
+    }
+
+    /// Where to read the baseline from, falling back to the legacy
+    /// `.revet-cache/baseline.json`
+    pub fn baseline_read_path(&self) -> PathBuf {
+        let legacy = self.legacy_dir.join(BASELINE_FILE);
+        if !self.baseline.exists() && legacy.exists() {
+            return legacy;
+        }
+        self.baseline.clone()
+    }
+
+    /// Where the baseline of one change is kept, by its `Change-Id` (see
+    /// [`StackChange::key`](crate::diff::StackChange::key))
+    pub fn change_baseline_path(&self, change: &str) -> PathBuf {
+        self.data_dir
+            .join(CHANGES_DIR)
+            .join(format!("{}.json", change))
+    }
+
+    /// Create the data directory
+    pub fn ensure_data_dir(&self) -> Result<()> {
+        std::fs::create_dir_all(&self.data_dir)
+            .with_context(|| format!("Failed to create {}", self.data_dir.display()))
+    }
+
+    /// Move everything in the legacy directory to the data directory (and
+    /// the baseline to the baseline path). Entries whose destination already
+    /// exists are left in place; the legacy directory is removed once empty.
+    pub fn migrate(&self) -> Result<MigrateReport> {
+        let mut report = MigrateReport::default();
+        if !self.is_relocated() && self.baseline == self.legacy_dir.join(BASELINE_FILE) {
+            return Ok(report);
+        }
+        let Ok(entries) = std::fs::read_dir(&self.legacy_dir) else {
+            return Ok(report);
+        };
+        let mut entries: Vec<_> = entries.filter_map(|e| e.ok()).collect();
+        entries.sort_by_key(|e| e.file_name());
+
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let target = if name == BASELINE_FILE {
+                self.baseline.clone()
+            } else {
+                self.data_dir.join(&name)
+            };
+            if target == entry.path() {
+                continue;
+            }
+            if target.exists() {
+                report.skipped.push(name);
+                continue;
+            }
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            move_entry(&entry.path(), &target)
+                .with_context(|| format!("Failed to move {} to {}", name, target.display()))?;
+            report.moved.push(name);
+        }
+
+        // Only succeeds when nothing was skipped
+        let _ = std::fs::remove_dir(&self.legacy_dir);
+        Ok(report)
+    }
+}
+
+/// `<name>-<hash>` for `repo_root`: the directory name and the first 16 hex
+/// digits of the SHA-256 of its `origin` URL, or of its absolute path when
+/// it has no `origin` remote
+pub fn repo_key(repo_root: &Path) -> String {
+    let absolute = std::fs::canonicalize(repo_root).unwrap_or_else(|_| repo_root.to_path_buf());
+    let identity = git2::Repository::open(&absolute)
+        .ok()
+        .and_then(|repo| {
+            let remote = repo.find_remote("origin").ok()?;
+            remote.url().map(str::to_string)
+        })
+        .unwrap_or_else(|| absolute.to_string_lossy().into_owned());
+
+    let digest = Sha256::digest(identity.as_bytes());
+    let hash: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    let name = absolute
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "repo".to_string());
+    format!("{}-{}", name, hash)
+}
+
+/// Rename `from` to `to`, copying and removing when they are on different
+/// filesystems
+fn move_entry(from: &Path, to: &Path) -> Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_entry(from, to)?;
+    if from.is_dir() {
+        std::fs::remove_dir_all(from)?;
+    } else {
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+fn copy_entry(from: &Path, to: &Path) -> Result<()> {
+    if from.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            copy_entry(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else {
+        std::fs::copy(from, to)?;
+    }
+    Ok(())
+}
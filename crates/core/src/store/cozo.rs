@@ -2,21 +2,201 @@
 //!
 //! Provides persistent, indexed graph storage using CozoDB with SQLite backend.
 //! Behind the `cozo-store` feature flag.
-
-use std::collections::BTreeMap;
+//!
+//! Snapshots are stored incrementally. Every `full_every`-th snapshot holds
+//! all node and edge records; the ones in between hold only the records that
+//! were added or changed since the previous snapshot (compared by a hash of
+//! each record) plus removal markers for the ones that went away. The
+//! `lineage` relation records which snapshot each delta applies to, and
+//! queries resolve a snapshot by walking that chain back to its full
+//! snapshot. Records are keyed by graph index, so deltas stay small as long
+//! as the graph keeps its node order between snapshots.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result};
 use cozo_ce::{DataValue, DbInstance, NamedRows, Num, ScriptMutability};
 
-use crate::graph::{Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeKind};
+use crate::graph::{Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeFingerprint, NodeKind};
 use crate::CodeGraph;
 
-use super::{EdgeResult, GraphStore, SnapshotInfo, StoreNodeId};
+use super::{changed_nodes, EdgeResult, GraphStore, SnapshotInfo, StoreNodeId};
+
+/// Snapshots between two full snapshots, counting the full one, unless
+/// configured with [`CozoStore::with_full_every`]
+pub const DEFAULT_FULL_EVERY: usize = 10;
+
+/// Rows imported per batch while flushing, so a flush never buffers the
+/// serialized graph as a whole
+const FLUSH_BATCH: usize = 4096;
+
+/// Node columns returned by queries, in [`CozoStore::deserialize_node`] order
+const NODE_FIELDS: &str = "id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json, fingerprint";
+
+/// Edge columns returned by queries, in [`CozoStore::deserialize_edge`] order
+const EDGE_FIELDS: &str = "from_id, to_id, edge_idx, kind, metadata_json";
+
+const NODE_HEADERS: &[&str] = &[
+    "snapshot",
+    "id",
+    "kind",
+    "name",
+    "file_path",
+    "line",
+    "end_line",
+    "data_json",
+    "decorators_json",
+    "type_params_json",
+    "fingerprint",
+    "hash",
+];
+
+const EDGE_HEADERS: &[&str] = &[
+    "snapshot",
+    "from_id",
+    "to_id",
+    "edge_idx",
+    "kind",
+    "metadata_json",
+    "hash",
+];
+
+/// The two record relations, which deltas and removal markers exist for
+#[derive(Debug, Clone, Copy)]
+enum Rel {
+    Nodes,
+    Edges,
+}
+
+impl Rel {
+    fn stored(self) -> &'static str {
+        match self {
+            Rel::Nodes => "nodes",
+            Rel::Edges => "edges",
+        }
+    }
+
+    fn removals(self) -> &'static str {
+        match self {
+            Rel::Nodes => "node_removals",
+            Rel::Edges => "edge_removals",
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Rel::Nodes => "id",
+            Rel::Edges => "from_id, to_id, edge_idx",
+        }
+    }
+
+    /// Every column, key first and `hash` last
+    fn columns(self) -> String {
+        match self {
+            Rel::Nodes => format!("{NODE_FIELDS}, hash"),
+            Rel::Edges => format!("{EDGE_FIELDS}, hash"),
+        }
+    }
+
+    /// `key => values` column spec for `:put` and `:rm`
+    fn spec(self) -> &'static str {
+        match self {
+            Rel::Nodes => "snapshot, id => kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json, fingerprint, hash",
+            Rel::Edges => "snapshot, from_id, to_id, edge_idx => kind, metadata_json, hash",
+        }
+    }
+}
+
+/// Where a snapshot sits in the lineage
+#[derive(Debug, Clone)]
+struct Lineage {
+    /// Snapshot this one is a delta against; empty for full snapshots
+    base: String,
+    /// Flush order
+    seq: i64,
+    /// Unix time of the flush, in seconds
+    created: i64,
+}
+
+/// What `revet snapshot gc` keeps. A snapshot survives when any configured
+/// rule keeps it, and the newest snapshot always does; with no rule set,
+/// nothing is removed.
+#[derive(Debug, Clone, Default)]
+pub struct Retention {
+    /// Keep the N newest snapshots
+    pub keep_last: Option<usize>,
+    /// Keep snapshots flushed within the last N days
+    pub keep_days: Option<u64>,
+}
+
+/// Outcome of [`CozoStore::gc`]
+#[derive(Debug, Clone, Default)]
+pub struct GcReport {
+    /// Snapshots deleted by the retention policy, oldest first
+    pub removed: Vec<String>,
+    /// Snapshots rewritten as full snapshots because their delta chain had
+    /// grown past `full_every`
+    pub compacted: Vec<String>,
+    /// Snapshots left in the store
+    pub kept: usize,
+}
+
+/// Rows for one stored relation, imported every [`FLUSH_BATCH`] rows
+struct BatchWriter<'a> {
+    db: &'a DbInstance,
+    relation: &'static str,
+    headers: &'static [&'static str],
+    rows: Vec<Vec<DataValue>>,
+}
+
+impl<'a> BatchWriter<'a> {
+    fn new(db: &'a DbInstance, relation: &'static str, headers: &'static [&'static str]) -> Self {
+        Self {
+            db,
+            relation,
+            headers,
+            rows: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, row: Vec<DataValue>) -> Result<()> {
+        self.rows.push(row);
+        if self.rows.len() >= FLUSH_BATCH {
+            self.write()?;
+        }
+        Ok(())
+    }
+
+    fn write(&mut self) -> Result<()> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+        let mut data = BTreeMap::new();
+        data.insert(
+            self.relation.to_string(),
+            NamedRows {
+                headers: self.headers.iter().map(|h| h.to_string()).collect(),
+                rows: std::mem::take(&mut self.rows),
+                next: None,
+            },
+        );
+        self.db
+            .import_relations(data)
+            .map_err(|e| anyhow::anyhow!("failed to import {}: {e}", self.relation))
+    }
+
+    fn finish(mut self) -> Result<()> {
+        self.write()
+    }
+}
 
 /// CozoDB-backed graph store
 pub struct CozoStore {
     db: DbInstance,
+    full_every: usize,
 }
 
 impl CozoStore {
@@ -24,7 +204,10 @@ impl CozoStore {
     pub fn new_memory() -> Result<Self> {
         let db = DbInstance::new("mem", "", Default::default())
             .map_err(|e| anyhow::anyhow!("failed to create CozoDB: {e}"))?;
-        let store = Self { db };
+        let store = Self {
+            db,
+            full_every: DEFAULT_FULL_EVERY,
+        };
         store.init_schema()?;
         Ok(store)
     }
@@ -33,11 +216,68 @@ impl CozoStore {
     pub fn new_sqlite(path: impl AsRef<Path>) -> Result<Self> {
         let db = DbInstance::new("sqlite", path.as_ref(), Default::default())
             .map_err(|e| anyhow::anyhow!("failed to create CozoDB with SQLite: {e}"))?;
-        let store = Self { db };
+        let store = Self {
+            db,
+            full_every: DEFAULT_FULL_EVERY,
+        };
         store.init_schema()?;
         Ok(store)
     }
 
+    /// Write a full snapshot every `n` flushes (1 writes every snapshot in
+    /// full); the others store only what changed since the previous one
+    pub fn with_full_every(mut self, n: usize) -> Self {
+        self.full_every = n.max(1);
+        self
+    }
+
+    /// The snapshot `snapshot` is stored as a delta against, or `None` when
+    /// it is a full snapshot (or does not exist)
+    pub fn base(&self, snapshot: &str) -> Result<Option<String>> {
+        Ok(self
+            .lineage()?
+            .remove(snapshot)
+            .map(|l| l.base)
+            .filter(|base| !base.is_empty()))
+    }
+
+    /// Delete the snapshots `retention` does not keep, then rewrite every
+    /// snapshot whose delta chain is longer than `full_every` as a full one.
+    /// `now` is the reference time for `keep_days`.
+    pub fn gc(&self, retention: &Retention, now: SystemTime) -> Result<GcReport> {
+        let mut newest_first: Vec<(String, Lineage)> = self.lineage()?.into_iter().collect();
+        newest_first.sort_by_key(|(_, l)| std::cmp::Reverse(l.seq));
+
+        let now = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+        let configured = retention.keep_last.is_some() || retention.keep_days.is_some();
+        let mut report = GcReport::default();
+        // Newest first: each removed delta folds into a kept child, so only
+        // the oldest removed full snapshot is copied in full
+        for (i, (name, lineage)) in newest_first.iter().enumerate() {
+            let by_count = retention.keep_last.is_some_and(|n| i < n);
+            let by_age = retention
+                .keep_days
+                .is_some_and(|days| now - lineage.created <= (days * 86_400) as i64);
+            if i == 0 || !configured || by_count || by_age {
+                continue;
+            }
+            self.delete_snapshot(name)?;
+            report.removed.push(name.clone());
+        }
+        report.removed.reverse();
+
+        let mut oldest_first: Vec<(String, Lineage)> = self.lineage()?.into_iter().collect();
+        oldest_first.sort_by_key(|(_, l)| l.seq);
+        for (name, _) in &oldest_first {
+            if self.chain(name)?.len() > self.full_every {
+                self.materialize(name)?;
+                report.compacted.push(name.clone());
+            }
+        }
+        report.kept = oldest_first.len();
+        Ok(report)
+    }
+
     /// Initialize the stored relations (tables)
     fn init_schema(&self) -> Result<()> {
         // Create nodes relation
@@ -53,7 +293,9 @@ impl CozoStore {
                 end_line: Int,
                 data_json: String,
                 decorators_json: String,
-                type_params_json: String
+                type_params_json: String,
+                fingerprint: String,
+                hash: Int
             }
         "#;
 
@@ -66,7 +308,24 @@ impl CozoStore {
                 edge_idx: Int
                 =>
                 kind: String,
-                metadata_json: String
+                metadata_json: String,
+                hash: Int
+            }
+        "#;
+
+        // Records a delta snapshot no longer has
+        let create_node_removals = r#"
+            :create node_removals {
+                snapshot: String,
+                id: Int
+            }
+        "#;
+        let create_edge_removals = r#"
+            :create edge_removals {
+                snapshot: String,
+                from_id: Int,
+                to_id: Int,
+                edge_idx: Int
             }
         "#;
 
@@ -80,7 +339,26 @@ impl CozoStore {
             }
         "#;
 
-        for script in [create_nodes, create_edges, create_snapshots] {
+        // Which snapshot each delta applies to ("" for full snapshots)
+        let create_lineage = r#"
+            :create lineage {
+                name: String
+                =>
+                base: String,
+                seq: Int,
+                created: Int
+            }
+        "#;
+
+        let scripts = [
+            create_nodes,
+            create_edges,
+            create_node_removals,
+            create_edge_removals,
+            create_snapshots,
+            create_lineage,
+        ];
+        for script in scripts {
             match self.run_mut(script) {
                 Ok(_) => {}
                 Err(e) => {
@@ -95,19 +373,63 @@ impl CozoStore {
             }
         }
 
-        // Verify the nodes schema has the type_params_json column
-        let probe = self.run_query(
-            "?[type_params_json] := *nodes{type_params_json} :limit 1",
-            BTreeMap::new(),
-        );
-        if probe.is_err() {
+        // Verify the schema has the record hashes and the lineage relation
+        let outdated = [
+            "?[hash] := *nodes{hash} :limit 1",
+            "?[seq] := *lineage{seq} :limit 1",
+        ]
+        .iter()
+        .any(|probe| self.run_query(probe, BTreeMap::new()).is_err());
+        if outdated {
             // Schema outdated — drop all relations and recreate
-            for rel in ["nodes", "edges", "snapshots"] {
+            for rel in [
+                "nodes",
+                "edges",
+                "node_removals",
+                "edge_removals",
+                "snapshots",
+                "lineage",
+            ] {
                 let _ = self.run_mut(&format!("::remove {rel}"));
             }
-            self.run_mut(create_nodes)?;
-            self.run_mut(create_edges)?;
-            self.run_mut(create_snapshots)?;
+            for script in scripts {
+                self.run_mut(script)?;
+            }
+        }
+
+        // Stores written before node fingerprints keep their snapshots: the
+        // column is added empty, and those nodes read back without one
+        if self
+            .run_query(
+                "?[fingerprint] := *nodes{fingerprint} :limit 1",
+                BTreeMap::new(),
+            )
+            .is_err()
+        {
+            self.run_mut(
+                r#"
+                ?[snapshot, id, kind, name, file_path, line, end_line, data_json,
+                  decorators_json, type_params_json, fingerprint, hash] :=
+                    *nodes{snapshot, id, kind, name, file_path, line, end_line, data_json,
+                           decorators_json, type_params_json, hash},
+                    fingerprint = ''
+                :replace nodes {
+                    snapshot: String,
+                    id: Int
+                    =>
+                    kind: String,
+                    name: String,
+                    file_path: String,
+                    line: Int,
+                    end_line: Int,
+                    data_json: String,
+                    decorators_json: String,
+                    type_params_json: String,
+                    fingerprint: String,
+                    hash: Int
+                }
+                "#,
+            )?;
         }
 
         Ok(())
@@ -134,8 +456,222 @@ impl CozoStore {
             .map_err(|e| anyhow::anyhow!("{e}"))
     }
 
+    /// Every snapshot's place in the lineage
+    fn lineage(&self) -> Result<HashMap<String, Lineage>> {
+        let result = self.run_query(
+            "?[name, base, seq, created] := *lineage{name, base, seq, created}",
+            BTreeMap::new(),
+        )?;
+        result
+            .rows
+            .iter()
+            .map(|row| {
+                Ok((
+                    row_str(&row[0])?.to_string(),
+                    Lineage {
+                        base: row_str(&row[1])?.to_string(),
+                        seq: row_int(&row[2])?,
+                        created: row_int(&row[3])?,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// `snapshot` followed by the snapshots its deltas apply to, ending at
+    /// a full snapshot. Unknown snapshots are their own (empty) chain.
+    fn chain(&self, snapshot: &str) -> Result<Vec<String>> {
+        let lineage = self.lineage()?;
+        let mut chain = vec![snapshot.to_string()];
+        while let Some(entry) = lineage.get(chain.last().map(String::as_str).unwrap_or_default()) {
+            if entry.base.is_empty() {
+                break;
+            }
+            if chain.len() > lineage.len() {
+                anyhow::bail!("snapshot lineage of '{snapshot}' has a cycle");
+            }
+            chain.push(entry.base.clone());
+        }
+        Ok(chain)
+    }
+
+    /// Datalog rules defining `rule[columns]` as the records of `rel` in
+    /// `snapshot`: for each key, the record of the newest snapshot in its
+    /// chain that has a record or removal marker for it. `filter` is added
+    /// to the rule bodies and may refer to any column.
+    fn view(
+        &self,
+        rule: &str,
+        rel: Rel,
+        snapshot: &str,
+        filter: &str,
+        params: &mut BTreeMap<String, DataValue>,
+    ) -> Result<String> {
+        let mut chain = self.chain(snapshot)?;
+        let root = chain.pop().unwrap_or_default();
+        params.insert(format!("{rule}_root"), DataValue::Str(root.into()));
+
+        let (stored, removals, key, columns) =
+            (rel.stored(), rel.removals(), rel.key(), rel.columns());
+        let filter = if filter.is_empty() {
+            String::new()
+        } else {
+            format!(", {filter}")
+        };
+        if chain.is_empty() {
+            return Ok(format!(
+                "{rule}[{columns}] := *{stored}{{snapshot: ${rule}_root, {columns}}}{filter}
+"
+            ));
+        }
+
+        let deltas = chain
+            .into_iter()
+            .zip(0_i64..)
+            .map(|(name, rank)| DataValue::List(vec![DataValue::Str(name.into()), rank.into()]))
+            .collect();
+        params.insert(format!("{rule}_chain"), DataValue::List(deltas));
+        Ok(format!(
+            "{rule}_chain[s, r] <- ${rule}_chain
+            {rule}_seen[{key}, min(r)] := {rule}_chain[s, r], *{stored}{{snapshot: s, {key}}}
+            {rule}_seen[{key}, min(r)] := {rule}_chain[s, r], *{removals}{{snapshot: s, {key}}}
+            {rule}_hit[{key}] := {rule}_seen[{key}, _]
+            {rule}[{columns}] := {rule}_seen[{key}, r], {rule}_chain[s, r],
+                *{stored}{{snapshot: s, {columns}}}{filter}
+            {rule}[{columns}] := *{stored}{{snapshot: ${rule}_root, {columns}}},
+                not {rule}_hit[{key}]{filter}
+            "
+        ))
+    }
+
+    /// Record hashes of `rel` in `snapshot`, by key
+    fn hashes(&self, rel: Rel, snapshot: &str) -> Result<HashMap<Vec<i64>, i64>> {
+        let mut params = BTreeMap::new();
+        let view = self.view("at", rel, snapshot, "", &mut params)?;
+        let key = rel.key();
+        let result = self.run_query(
+            &format!(
+                "{view}?[{key}, hash] := at[{columns}]",
+                columns = rel.columns()
+            ),
+            params,
+        )?;
+        result
+            .rows
+            .iter()
+            .map(|row| {
+                let (hash, key) = row.split_last().context("empty row")?;
+                let key = key.iter().map(row_int).collect::<Result<Vec<_>>>()?;
+                Ok((key, row_int(hash)?))
+            })
+            .collect()
+    }
+
+    /// Fold `parent`'s records into its delta `child`, so that `child`
+    /// applies to `parent`'s own base (or becomes full) and `parent` can be
+    /// deleted
+    fn fold(&self, parent: &str, child: &str) -> Result<()> {
+        let parent_base = self
+            .lineage()?
+            .remove(parent)
+            .map(|l| l.base)
+            .unwrap_or_default();
+        let params = || {
+            BTreeMap::from([
+                ("parent".to_string(), DataValue::Str(parent.into())),
+                ("child".to_string(), DataValue::Str(child.into())),
+                (
+                    "base".to_string(),
+                    DataValue::Str(parent_base.as_str().into()),
+                ),
+            ])
+        };
+
+        for rel in [Rel::Nodes, Rel::Edges] {
+            let (stored, removals, key, columns, spec) = (
+                rel.stored(),
+                rel.removals(),
+                rel.key(),
+                rel.columns(),
+                rel.spec(),
+            );
+            // The parent's records the child neither replaces nor removes
+            self.run_mut_with(
+                &format!(
+                    "?[snapshot, {columns}] := *{stored}{{snapshot: $parent, {columns}}},
+                        snapshot = $child,
+                        not *{stored}{{snapshot: $child, {key}}},
+                        not *{removals}{{snapshot: $child, {key}}}
+                    :put {stored} {{{spec}}}"
+                ),
+                params(),
+            )?;
+            if parent_base.is_empty() {
+                // Nothing below a full snapshot to remove records from
+                self.run_mut_with(
+                    &format!(
+                        "?[snapshot, {key}] := *{removals}{{snapshot, {key}}}, snapshot = $child
+                        :rm {removals} {{snapshot, {key}}}"
+                    ),
+                    params(),
+                )?;
+            } else {
+                self.run_mut_with(
+                    &format!(
+                        "?[snapshot, {key}] := *{removals}{{snapshot: $parent, {key}}},
+                            snapshot = $child,
+                            not *{stored}{{snapshot: $child, {key}}}
+                        :put {removals} {{snapshot, {key}}}"
+                    ),
+                    params(),
+                )?;
+            }
+        }
+
+        self.run_mut_with(
+            "?[name, base, seq, created] := *lineage{name, seq, created}, name = $child, base = $base
+            :put lineage {name => base, seq, created}",
+            params(),
+        )?;
+        Ok(())
+    }
+
+    /// Rewrite `snapshot` as a full snapshot
+    fn materialize(&self, snapshot: &str) -> Result<()> {
+        while let Some(base) = self.base(snapshot)? {
+            self.fold(&base, snapshot)?;
+        }
+        Ok(())
+    }
+
+    /// Delete the stored rows of one snapshot, leaving its dependents alone
+    fn remove_rows(&self, snapshot: &str) -> Result<()> {
+        let params = || BTreeMap::from([("snap".to_string(), DataValue::Str(snapshot.into()))]);
+        for rel in [Rel::Nodes, Rel::Edges] {
+            let (stored, removals, key) = (rel.stored(), rel.removals(), rel.key());
+            for relation in [stored, removals] {
+                self.run_mut_with(
+                    &format!(
+                        "?[snapshot, {key}] := *{relation}{{snapshot, {key}}}, snapshot = $snap
+                        :rm {relation} {{snapshot, {key}}}"
+                    ),
+                    params(),
+                )?;
+            }
+        }
+        self.run_mut_with(
+            "?[name] := *snapshots{name}, name = $snap :rm snapshots {name}",
+            params(),
+        )?;
+        self.run_mut_with(
+            "?[name] := *lineage{name}, name = $snap :rm lineage {name}",
+            params(),
+        )?;
+        Ok(())
+    }
+
     /// Deserialize a Node from a CozoDB row
-    /// Expected columns: id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json
+    /// Expected columns: id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json, fingerprint
     fn deserialize_node(row: &[DataValue]) -> Result<(StoreNodeId, Node)> {
         let id = row_int(&row[0])? as u64;
         let kind_str = row_str(&row[1])?;
@@ -146,6 +682,7 @@ impl CozoStore {
         let data_json = row_str(&row[6])?;
         let decorators_json = row_str(&row[7])?;
         let type_params_json = row_str(&row[8])?;
+        let fingerprint = row_str(&row[9])?;
 
         let kind: NodeKind =
             serde_json::from_str(&format!("\"{kind_str}\"")).context("invalid NodeKind")?;
@@ -170,16 +707,21 @@ impl CozoStore {
             node.set_type_parameters(type_params);
         }
 
+        // Empty in snapshots stored before fingerprints
+        if !fingerprint.is_empty() {
+            node.set_fingerprint(fingerprint.parse()?);
+        }
+
         Ok((StoreNodeId(id), node))
     }
 
     /// Deserialize an Edge from a CozoDB row
-    /// Expected columns: from_id, to_id, kind, metadata_json
+    /// Expected columns: from_id, to_id, edge_idx, kind, metadata_json
     fn deserialize_edge(row: &[DataValue]) -> Result<EdgeResult> {
         let from_id = row_int(&row[0])? as u64;
         let to_id = row_int(&row[1])? as u64;
-        let kind_str = row_str(&row[2])?;
-        let metadata_json = row_str(&row[3])?;
+        let kind_str = row_str(&row[3])?;
+        let metadata_json = row_str(&row[4])?;
 
         let kind: EdgeKind =
             serde_json::from_str(&format!("\"{kind_str}\"")).context("invalid EdgeKind")?;
@@ -228,126 +770,156 @@ fn kind_to_string<T: serde::Serialize>(kind: &T) -> String {
     json.trim_matches('"').to_string()
 }
 
+/// Value columns of a node record, between its key and its hash
+fn node_values(node: &Node, fingerprint: &NodeFingerprint) -> Result<Vec<DataValue>> {
+    let end_line = node.end_line().map(|l| l as i64).unwrap_or(-1);
+    Ok(vec![
+        DataValue::Str(kind_to_string(node.kind()).into()),
+        DataValue::Str(node.name().into()),
+        DataValue::Str(node.file_path().display().to_string().into()),
+        DataValue::from(node.line() as i64),
+        DataValue::from(end_line),
+        DataValue::Str(serde_json::to_string(node.data())?.into()),
+        DataValue::Str(serde_json::to_string(node.decorators())?.into()),
+        DataValue::Str(serde_json::to_string(node.type_parameters())?.into()),
+        DataValue::Str(fingerprint.to_string().into()),
+    ])
+}
+
+/// Value columns of an edge record, between its key and its hash
+fn edge_values(edge: &Edge) -> Result<Vec<DataValue>> {
+    let metadata_json = match edge.metadata() {
+        Some(m) => serde_json::to_string(m)?,
+        None => String::new(),
+    };
+    Ok(vec![
+        DataValue::Str(kind_to_string(edge.kind()).into()),
+        DataValue::Str(metadata_json.into()),
+    ])
+}
+
+/// Content hash of a record's value columns, compared between snapshots to
+/// decide what a delta stores
+fn record_hash(values: &[DataValue]) -> i64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    values.hash(&mut hasher);
+    hasher.finish() as i64
+}
+
+/// Unix time in seconds
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
 impl GraphStore for CozoStore {
     fn flush(&self, graph: &CodeGraph, snapshot: &str) -> Result<()> {
-        // Delete existing snapshot data first
+        // Replace an existing snapshot of the same name
         self.delete_snapshot(snapshot)?;
 
-        // Build node rows as DataValue vectors for import_relations
-        let mut node_rows: Vec<Vec<DataValue>> = Vec::new();
+        // Delta against the newest snapshot, unless its chain is already
+        // `full_every` long
+        let lineage = self.lineage()?;
+        let parent = lineage.iter().max_by_key(|(_, l)| l.seq);
+        let seq = parent.map(|(_, l)| l.seq + 1).unwrap_or(0);
+        let base = match parent {
+            Some((name, _)) if self.chain(name)?.len() < self.full_every => name.clone(),
+            _ => String::new(),
+        };
+        let (mut old_nodes, mut old_edges) = if base.is_empty() {
+            (HashMap::new(), HashMap::new())
+        } else {
+            (
+                self.hashes(Rel::Nodes, &base)?,
+                self.hashes(Rel::Edges, &base)?,
+            )
+        };
+
+        // Records are written as they are built, in batches, and only when
+        // the base snapshot has no identical record under the same key.
+        // Lineage is written last, so an interrupted flush leaves no
+        // snapshot behind, and flushing it again clears its partial rows.
+        let snap = DataValue::Str(snapshot.into());
+        let mut nodes = BatchWriter::new(&self.db, "nodes", NODE_HEADERS);
+        let mut edges = BatchWriter::new(&self.db, "edges", EDGE_HEADERS);
+        let (mut node_count, mut edge_count) = (0_i64, 0_i64);
+        let fingerprints = graph.fingerprints();
         for (node_id, node) in graph.nodes() {
-            let data_json = serde_json::to_string(node.data())?;
-            let end_line = node.end_line().map(|l| l as i64).unwrap_or(-1);
-            let decorators_json = serde_json::to_string(node.decorators())?;
-            let type_params_json = serde_json::to_string(node.type_parameters())?;
-
-            node_rows.push(vec![
-                DataValue::Str(snapshot.into()),
-                DataValue::from(node_id.index() as i64),
-                DataValue::Str(kind_to_string(node.kind()).into()),
-                DataValue::Str(node.name().into()),
-                DataValue::Str(node.file_path().display().to_string().into()),
-                DataValue::from(node.line() as i64),
-                DataValue::from(end_line),
-                DataValue::Str(data_json.into()),
-                DataValue::Str(decorators_json.into()),
-                DataValue::Str(type_params_json.into()),
-            ]);
-        }
+            let id = node_id.index() as i64;
+            let values = node_values(node, &fingerprints[node_id.index()])?;
+            let hash = record_hash(&values);
+            node_count += 1;
+            if old_nodes.remove(&vec![id]) != Some(hash) {
+                let mut row = vec![snap.clone(), DataValue::from(id)];
+                row.extend(values);
+                row.push(DataValue::from(hash));
+                nodes.push(row)?;
+            }
 
-        // Build edge rows
-        let mut edge_rows: Vec<Vec<DataValue>> = Vec::new();
-        let mut edge_count: i64 = 0;
-        for (node_id, _) in graph.nodes() {
-            let from_id = node_id.index() as i64;
-            for (edge_idx_counter, (target, edge)) in (0_i64..).zip(graph.edges_from(node_id)) {
+            for (edge_idx, (target, edge)) in (0_i64..).zip(graph.edges_from(node_id)) {
                 let to_id = target.index() as i64;
-                let metadata_json = match edge.metadata() {
-                    Some(m) => serde_json::to_string(m)?,
-                    None => String::new(),
-                };
-
-                edge_rows.push(vec![
-                    DataValue::Str(snapshot.into()),
-                    DataValue::from(from_id),
-                    DataValue::from(to_id),
-                    DataValue::from(edge_idx_counter),
-                    DataValue::Str(kind_to_string(edge.kind()).into()),
-                    DataValue::Str(metadata_json.into()),
-                ]);
-
+                let values = edge_values(edge)?;
+                let hash = record_hash(&values);
                 edge_count += 1;
+                if old_edges.remove(&vec![id, to_id, edge_idx]) != Some(hash) {
+                    let mut row = vec![
+                        snap.clone(),
+                        DataValue::from(id),
+                        DataValue::from(to_id),
+                        DataValue::from(edge_idx),
+                    ];
+                    row.extend(values);
+                    row.push(DataValue::from(hash));
+                    edges.push(row)?;
+                }
             }
         }
-
-        // Use import_relations for bulk insertion (avoids CozoScript string escaping)
-        let node_count = node_rows.len() as i64;
-
-        if !node_rows.is_empty() {
-            let mut data = BTreeMap::new();
-            data.insert(
-                "nodes".to_string(),
-                NamedRows {
-                    headers: vec![
-                        "snapshot".into(),
-                        "id".into(),
-                        "kind".into(),
-                        "name".into(),
-                        "file_path".into(),
-                        "line".into(),
-                        "end_line".into(),
-                        "data_json".into(),
-                        "decorators_json".into(),
-                        "type_params_json".into(),
-                    ],
-                    rows: node_rows,
-                    next: None,
-                },
-            );
-            self.db
-                .import_relations(data)
-                .map_err(|e| anyhow::anyhow!("failed to import nodes: {e}"))?;
-        }
-
-        if !edge_rows.is_empty() {
-            let mut data = BTreeMap::new();
-            data.insert(
-                "edges".to_string(),
-                NamedRows {
-                    headers: vec![
-                        "snapshot".into(),
-                        "from_id".into(),
-                        "to_id".into(),
-                        "edge_idx".into(),
-                        "kind".into(),
-                        "metadata_json".into(),
-                    ],
-                    rows: edge_rows,
-                    next: None,
-                },
-            );
-            self.db
-                .import_relations(data)
-                .map_err(|e| anyhow::anyhow!("failed to import edges: {e}"))?;
+        nodes.finish()?;
+        edges.finish()?;
+
+        // Whatever the base had and the graph no longer does
+        let mut node_removals = BatchWriter::new(&self.db, "node_removals", &["snapshot", "id"]);
+        for key in old_nodes.into_keys() {
+            node_removals.push(
+                std::iter::once(snap.clone())
+                    .chain(key.into_iter().map(DataValue::from))
+                    .collect(),
+            )?;
         }
-
-        // Record snapshot metadata
-        let mut snap_data = BTreeMap::new();
-        snap_data.insert(
-            "snapshots".to_string(),
-            NamedRows {
-                headers: vec!["name".into(), "node_count".into(), "edge_count".into()],
-                rows: vec![vec![
-                    DataValue::Str(snapshot.into()),
-                    DataValue::from(node_count),
-                    DataValue::from(edge_count),
-                ]],
-                next: None,
-            },
+        node_removals.finish()?;
+        let mut edge_removals = BatchWriter::new(
+            &self.db,
+            "edge_removals",
+            &["snapshot", "from_id", "to_id", "edge_idx"],
         );
-        self.db
-            .import_relations(snap_data)
-            .map_err(|e| anyhow::anyhow!("failed to import snapshot metadata: {e}"))?;
+        for key in old_edges.into_keys() {
+            edge_removals.push(
+                std::iter::once(snap.clone())
+                    .chain(key.into_iter().map(DataValue::from))
+                    .collect(),
+            )?;
+        }
+        edge_removals.finish()?;
+
+        let mut snapshots =
+            BatchWriter::new(&self.db, "snapshots", &["name", "node_count", "edge_count"]);
+        snapshots.push(vec![
+            snap.clone(),
+            DataValue::from(node_count),
+            DataValue::from(edge_count),
+        ])?;
+        snapshots.finish()?;
+        let mut lineage =
+            BatchWriter::new(&self.db, "lineage", &["name", "base", "seq", "created"]);
+        lineage.push(vec![
+            snap,
+            DataValue::Str(base.into()),
+            DataValue::from(seq),
+            DataValue::from(unix_now()),
+        ])?;
+        lineage.finish()?;
 
         Ok(())
     }
@@ -372,53 +944,26 @@ impl GraphStore for CozoStore {
     }
 
     fn delete_snapshot(&self, snapshot: &str) -> Result<()> {
-        let snap_val = DataValue::Str(snapshot.into());
-
-        // Delete nodes
-        let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), snap_val.clone());
-        let _ = self.run_mut_with(
-            r#"?[snapshot, id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json] :=
-                *nodes{snapshot, id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json},
-                snapshot = $snap
-            :delete nodes {snapshot, id => kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json}"#,
-            params,
-        );
-
-        // Delete edges
-        let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), snap_val.clone());
-        let _ = self.run_mut_with(
-            r#"?[snapshot, from_id, to_id, edge_idx, kind, metadata_json] :=
-                *edges{snapshot, from_id, to_id, edge_idx, kind, metadata_json},
-                snapshot = $snap
-            :delete edges {snapshot, from_id, to_id, edge_idx => kind, metadata_json}"#,
-            params,
-        );
-
-        // Delete snapshot metadata
-        let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), snap_val);
-        let _ = self.run_mut_with(
-            r#"?[name, node_count, edge_count] :=
-                *snapshots{name, node_count, edge_count},
-                name = $snap
-            :delete snapshots {name => node_count, edge_count}"#,
-            params,
-        );
-
-        Ok(())
+        // Deltas against this snapshot take over its records first
+        let mut children: Vec<(String, Lineage)> = self
+            .lineage()?
+            .into_iter()
+            .filter(|(_, l)| l.base == snapshot)
+            .collect();
+        children.sort_by_key(|(_, l)| l.seq);
+        for (child, _) in children {
+            self.fold(snapshot, &child)?;
+        }
+        self.remove_rows(snapshot)
     }
 
     fn node(&self, id: StoreNodeId, snapshot: &str) -> Result<Option<Node>> {
         let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), DataValue::Str(snapshot.into()));
         params.insert("id".to_string(), DataValue::from(id.0 as i64));
+        let view = self.view("at", Rel::Nodes, snapshot, "id = $id", &mut params)?;
 
         let result = self.run_query(
-            r#"?[id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json] :=
-                *nodes{snapshot, id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json},
-                snapshot = $snap, id = $id"#,
+            &format!("{view}?[{NODE_FIELDS}] := at[{NODE_FIELDS}, _]"),
             params,
         )?;
 
@@ -432,12 +977,10 @@ impl GraphStore for CozoStore {
 
     fn nodes(&self, snapshot: &str) -> Result<Vec<(StoreNodeId, Node)>> {
         let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), DataValue::Str(snapshot.into()));
+        let view = self.view("at", Rel::Nodes, snapshot, "", &mut params)?;
 
         let result = self.run_query(
-            r#"?[id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json] :=
-                *nodes{snapshot, id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json},
-                snapshot = $snap"#,
+            &format!("{view}?[{NODE_FIELDS}] := at[{NODE_FIELDS}, _]"),
             params,
         )?;
 
@@ -455,21 +998,20 @@ impl GraphStore for CozoStore {
         snapshot: &str,
     ) -> Result<Vec<(StoreNodeId, Node)>> {
         let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), DataValue::Str(snapshot.into()));
         params.insert("fp".to_string(), DataValue::Str(file_path.into()));
 
-        let script = if let Some(n) = name {
+        let filter = if let Some(n) = name {
             params.insert("name".to_string(), DataValue::Str(n.into()));
-            r#"?[id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json] :=
-                *nodes{snapshot, id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json},
-                snapshot = $snap, file_path = $fp, name = $name"#
+            "file_path = $fp, name = $name"
         } else {
-            r#"?[id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json] :=
-                *nodes{snapshot, id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json},
-                snapshot = $snap, file_path = $fp"#
+            "file_path = $fp"
         };
+        let view = self.view("at", Rel::Nodes, snapshot, filter, &mut params)?;
 
-        let result = self.run_query(script, params)?;
+        let result = self.run_query(
+            &format!("{view}?[{NODE_FIELDS}] := at[{NODE_FIELDS}, _]"),
+            params,
+        )?;
         result
             .rows
             .iter()
@@ -483,16 +1025,14 @@ impl GraphStore for CozoStore {
         snapshot: &str,
     ) -> Result<Vec<(StoreNodeId, Node)>> {
         let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), DataValue::Str(snapshot.into()));
         params.insert(
             "kind".to_string(),
             DataValue::Str(kind_to_string(&kind).into()),
         );
+        let view = self.view("at", Rel::Nodes, snapshot, "kind = $kind", &mut params)?;
 
         let result = self.run_query(
-            r#"?[id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json] :=
-                *nodes{snapshot, id, kind, name, file_path, line, end_line, data_json, decorators_json, type_params_json},
-                snapshot = $snap, kind = $kind"#,
+            &format!("{view}?[{NODE_FIELDS}] := at[{NODE_FIELDS}, _]"),
             params,
         )?;
 
@@ -505,10 +1045,10 @@ impl GraphStore for CozoStore {
 
     fn node_count(&self, snapshot: &str) -> Result<usize> {
         let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), DataValue::Str(snapshot.into()));
+        let view = self.view("at", Rel::Nodes, snapshot, "", &mut params)?;
 
         let result = self.run_query(
-            r#"?[count(id)] := *nodes{snapshot, id}, snapshot = $snap"#,
+            &format!("{view}?[count(id)] := at[{NODE_FIELDS}, _]"),
             params,
         )?;
 
@@ -518,15 +1058,29 @@ impl GraphStore for CozoStore {
         Ok(row_int(&result.rows[0][0])? as usize)
     }
 
+    fn edges(&self, snapshot: &str) -> Result<Vec<EdgeResult>> {
+        let mut params = BTreeMap::new();
+        let view = self.view("at", Rel::Edges, snapshot, "", &mut params)?;
+
+        let result = self.run_query(
+            &format!("{view}?[{EDGE_FIELDS}] := at[{EDGE_FIELDS}, _]"),
+            params,
+        )?;
+
+        result
+            .rows
+            .iter()
+            .map(|row| Self::deserialize_edge(row))
+            .collect()
+    }
+
     fn edges_from(&self, node: StoreNodeId, snapshot: &str) -> Result<Vec<EdgeResult>> {
         let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), DataValue::Str(snapshot.into()));
         params.insert("from".to_string(), DataValue::from(node.0 as i64));
+        let view = self.view("at", Rel::Edges, snapshot, "from_id = $from", &mut params)?;
 
         let result = self.run_query(
-            r#"?[from_id, to_id, kind, metadata_json] :=
-                *edges{snapshot, from_id, to_id, kind, metadata_json},
-                snapshot = $snap, from_id = $from"#,
+            &format!("{view}?[{EDGE_FIELDS}] := at[{EDGE_FIELDS}, _]"),
             params,
         )?;
 
@@ -539,13 +1093,11 @@ impl GraphStore for CozoStore {
 
     fn edges_to(&self, node: StoreNodeId, snapshot: &str) -> Result<Vec<EdgeResult>> {
         let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), DataValue::Str(snapshot.into()));
         params.insert("to".to_string(), DataValue::from(node.0 as i64));
+        let view = self.view("at", Rel::Edges, snapshot, "to_id = $to", &mut params)?;
 
         let result = self.run_query(
-            r#"?[from_id, to_id, kind, metadata_json] :=
-                *edges{snapshot, from_id, to_id, kind, metadata_json},
-                snapshot = $snap, to_id = $to"#,
+            &format!("{view}?[{EDGE_FIELDS}] := at[{EDGE_FIELDS}, _]"),
             params,
         )?;
 
@@ -558,12 +1110,11 @@ impl GraphStore for CozoStore {
 
     fn direct_dependents(&self, node: StoreNodeId, snapshot: &str) -> Result<Vec<StoreNodeId>> {
         let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), DataValue::Str(snapshot.into()));
         params.insert("target".to_string(), DataValue::from(node.0 as i64));
+        let view = self.view("at", Rel::Edges, snapshot, "to_id = $target", &mut params)?;
 
         let result = self.run_query(
-            r#"?[from_id] := *edges{snapshot, from_id, to_id},
-                snapshot = $snap, to_id = $target"#,
+            &format!("{view}?[from_id] := at[from_id, _, _, _, _, _]"),
             params,
         )?;
 
@@ -582,22 +1133,22 @@ impl GraphStore for CozoStore {
     ) -> Result<Vec<StoreNodeId>> {
         // Use recursive Datalog for transitive closure
         let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), DataValue::Str(snapshot.into()));
         params.insert("start".to_string(), DataValue::from(node.0 as i64));
+        let view = self.view("at", Rel::Edges, snapshot, "", &mut params)?;
 
-        let script = if let Some(depth) = max_depth {
+        let rules = if let Some(depth) = max_depth {
             // With depth limit: track depth in the recursion
             params.insert("max_depth".to_string(), DataValue::from(depth as i64));
-            r#"dep[x, d] := *edges{snapshot, from_id: x, to_id: $start}, snapshot = $snap, d = 1
-               dep[x, d] := dep[y, d1], *edges{snapshot, from_id: x, to_id: y}, snapshot = $snap, d = d1 + 1, d <= $max_depth
+            r#"dep[x, d] := at[x, t, _, _, _, _], t = $start, d = 1
+               dep[x, d] := dep[y, d1], at[x, y, _, _, _, _], d = d1 + 1, d <= $max_depth
                ?[x] := dep[x, _]"#
         } else {
-            r#"dep[x] := *edges{snapshot, from_id: x, to_id: $start}, snapshot = $snap
-               dep[x] := dep[y], *edges{snapshot, from_id: x, to_id: y}, snapshot = $snap
+            r#"dep[x] := at[x, t, _, _, _, _], t = $start
+               dep[x] := dep[y], at[x, y, _, _, _, _]
                ?[x] := dep[x]"#
         };
 
-        let result = self.run_query(script, params)?;
+        let result = self.run_query(&format!("{view}{rules}"), params)?;
 
         result
             .rows
@@ -608,12 +1159,11 @@ impl GraphStore for CozoStore {
 
     fn dependencies(&self, node: StoreNodeId, snapshot: &str) -> Result<Vec<StoreNodeId>> {
         let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), DataValue::Str(snapshot.into()));
         params.insert("source".to_string(), DataValue::from(node.0 as i64));
+        let view = self.view("at", Rel::Edges, snapshot, "from_id = $source", &mut params)?;
 
         let result = self.run_query(
-            r#"?[to_id] := *edges{snapshot, from_id, to_id},
-                snapshot = $snap, from_id = $source"#,
+            &format!("{view}?[to_id] := at[_, to_id, _, _, _, _]"),
             params,
         )?;
 
@@ -631,21 +1181,21 @@ impl GraphStore for CozoStore {
         snapshot: &str,
     ) -> Result<Vec<StoreNodeId>> {
         let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), DataValue::Str(snapshot.into()));
         params.insert("start".to_string(), DataValue::from(node.0 as i64));
+        let view = self.view("at", Rel::Edges, snapshot, "", &mut params)?;
 
-        let script = if let Some(depth) = max_depth {
+        let rules = if let Some(depth) = max_depth {
             params.insert("max_depth".to_string(), DataValue::from(depth as i64));
-            r#"dep[x, d] := *edges{snapshot, from_id: $start, to_id: x}, snapshot = $snap, d = 1
-               dep[x, d] := dep[y, d1], *edges{snapshot, from_id: y, to_id: x}, snapshot = $snap, d = d1 + 1, d <= $max_depth
+            r#"dep[x, d] := at[s, x, _, _, _, _], s = $start, d = 1
+               dep[x, d] := dep[y, d1], at[y, x, _, _, _, _], d = d1 + 1, d <= $max_depth
                ?[x] := dep[x, _]"#
         } else {
-            r#"dep[x] := *edges{snapshot, from_id: $start, to_id: x}, snapshot = $snap
-               dep[x] := dep[y], *edges{snapshot, from_id: y, to_id: x}, snapshot = $snap
+            r#"dep[x] := at[s, x, _, _, _, _], s = $start
+               dep[x] := dep[y], at[y, x, _, _, _, _]
                ?[x] := dep[x]"#
         };
 
-        let result = self.run_query(script, params)?;
+        let result = self.run_query(&format!("{view}{rules}"), params)?;
 
         result
             .rows
@@ -661,16 +1211,21 @@ impl GraphStore for CozoStore {
         snapshot: &str,
     ) -> Result<Vec<StoreNodeId>> {
         let mut params = BTreeMap::new();
-        params.insert("snap".to_string(), DataValue::Str(snapshot.into()));
         params.insert("source".to_string(), DataValue::from(node.0 as i64));
         params.insert(
             "kind".to_string(),
             DataValue::Str(kind_to_string(&kind).into()),
         );
+        let view = self.view(
+            "at",
+            Rel::Edges,
+            snapshot,
+            "from_id = $source, kind = $kind",
+            &mut params,
+        )?;
 
         let result = self.run_query(
-            r#"?[to_id] := *edges{snapshot, from_id, to_id, kind},
-                snapshot = $snap, from_id = $source, kind = $kind"#,
+            &format!("{view}?[to_id] := at[_, to_id, _, _, _, _]"),
             params,
         )?;
 
@@ -686,44 +1241,6 @@ impl GraphStore for CozoStore {
         old_snapshot: &str,
         new_snapshot: &str,
     ) -> Result<Vec<(StoreNodeId, Option<StoreNodeId>)>> {
-        let mut params = BTreeMap::new();
-        params.insert("new_snap".to_string(), DataValue::Str(new_snapshot.into()));
-        params.insert("old_snap".to_string(), DataValue::Str(old_snapshot.into()));
-
-        // Find modified nodes: same file_path + name + kind but different data or line
-        // Find added nodes: exist in new but not in old
-        // Split into separate queries for line changes and data changes to avoid 'or'
-        let script = r#"
-            changed[new_id, old_id] :=
-                *nodes{snapshot: $new_snap, id: new_id, kind, name, file_path, line: new_line, data_json},
-                *nodes{snapshot: $old_snap, id: old_id, kind, name, file_path, line: old_line, data_json},
-                new_line != old_line
-            changed[new_id, old_id] :=
-                *nodes{snapshot: $new_snap, id: new_id, kind, name, file_path, data_json: new_data},
-                *nodes{snapshot: $old_snap, id: old_id, kind, name, file_path, data_json: old_data},
-                new_data != old_data
-            added[new_id] :=
-                *nodes{snapshot: $new_snap, id: new_id, kind, name, file_path},
-                not *nodes{snapshot: $old_snap, kind, name, file_path}
-            ?[new_id, old_id] := changed[new_id, old_id]
-            ?[new_id, old_id] := added[new_id], old_id = -1
-        "#;
-
-        let result = self.run_query(script, params)?;
-
-        result
-            .rows
-            .iter()
-            .map(|row| {
-                let new_id = StoreNodeId(row_int(&row[0])? as u64);
-                let old_id_val = row_int(&row[1])?;
-                let old_id = if old_id_val < 0 {
-                    None
-                } else {
-                    Some(StoreNodeId(old_id_val as u64))
-                };
-                Ok((new_id, old_id))
-            })
-            .collect()
+        changed_nodes(self, old_snapshot, new_snapshot)
     }
 }
@@ -8,7 +8,7 @@ use anyhow::{Context, Result};
 use crate::graph::{EdgeKind, Node, NodeKind};
 use crate::CodeGraph;
 
-use super::{EdgeResult, GraphStore, SnapshotInfo, StoreNodeId};
+use super::{changed_nodes, EdgeResult, GraphStore, SnapshotInfo, StoreNodeId};
 
 /// In-memory graph store backed by `HashMap<String, CodeGraph>`
 pub struct MemoryStore {
@@ -260,37 +260,6 @@ impl GraphStore for MemoryStore {
         old_snapshot: &str,
         new_snapshot: &str,
     ) -> Result<Vec<(StoreNodeId, Option<StoreNodeId>)>> {
-        let graphs = self
-            .graphs
-            .read()
-            .map_err(|e| anyhow::anyhow!("lock poisoned: {e}"))?;
-        let old_graph = graphs.get(old_snapshot).context("old snapshot not found")?;
-        let new_graph = graphs.get(new_snapshot).context("new snapshot not found")?;
-
-        let mut changed = Vec::new();
-
-        for (new_id, new_node) in new_graph.nodes() {
-            // Find matching node in old graph by file_path + name + kind
-            let old_match = old_graph.nodes().find(|(_, old_node)| {
-                old_node.file_path() == new_node.file_path()
-                    && old_node.name() == new_node.name()
-                    && old_node.kind() == new_node.kind()
-            });
-
-            match old_match {
-                Some((old_id, old_node)) => {
-                    // Check if data or line changed
-                    if old_node.data() != new_node.data() || old_node.line() != new_node.line() {
-                        changed.push((StoreNodeId::from(new_id), Some(StoreNodeId::from(old_id))));
-                    }
-                }
-                None => {
-                    // New node (added)
-                    changed.push((StoreNodeId::from(new_id), None));
-                }
-            }
-        }
-
-        Ok(changed)
+        changed_nodes(self, old_snapshot, new_snapshot)
     }
 }
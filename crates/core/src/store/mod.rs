@@ -13,14 +13,16 @@ pub mod cozo;
 pub use memory::MemoryStore;
 
 #[cfg(feature = "cozo-store")]
-pub use cozo::CozoStore;
+pub use cozo::{CozoStore, GcReport, Retention};
 
 use std::collections::HashMap;
 use std::path::Path;
 
-use crate::graph::{Edge, EdgeKind, Node, NodeId, NodeKind};
+use crate::graph::{fingerprint, Edge, EdgeKind, Node, NodeId, NodeKind};
+#[cfg(feature = "cozo-store")]
+use crate::storage::{StoragePaths, GRAPH_DB};
 use crate::CodeGraph;
-use anyhow::{Context, Result};
+use anyhow::Result;
 
 /// Storage-agnostic node identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -99,6 +101,18 @@ pub trait GraphStore: Send + Sync {
 
     // -- Edge queries --
 
+    /// Get every edge in a snapshot, grouped by source node in store ID
+    /// order and, per source, in the order `edges_from` returns them
+    fn edges(&self, snapshot: &str) -> Result<Vec<EdgeResult>> {
+        let mut nodes = self.nodes(snapshot)?;
+        nodes.sort_by_key(|(id, _)| id.0);
+        let mut edges = Vec::new();
+        for (id, _) in nodes {
+            edges.extend(self.edges_from(id, snapshot)?);
+        }
+        Ok(edges)
+    }
+
     /// Get all outgoing edges from a node
     fn edges_from(&self, node: StoreNodeId, snapshot: &str) -> Result<Vec<EdgeResult>>;
 
@@ -151,12 +165,25 @@ pub trait GraphStore: Send + Sync {
 /// Reconstruct a `CodeGraph` from a store snapshot.
 ///
 /// Loads all nodes (sorted by StoreNodeId for deterministic NodeId assignment),
-/// maps them into a new CodeGraph, then adds all edges.
+/// maps them into a new CodeGraph, then adds all edges. A snapshot yields the
+/// same graph whether the store holds it in full or as a delta chain. Nodes
+/// stored before fingerprints existed get them assigned.
 pub fn reconstruct_graph(
     store: &dyn GraphStore,
     snapshot: &str,
     root_path: &Path,
 ) -> Result<CodeGraph> {
+    let (mut graph, _) = load_graph(store, snapshot, root_path)?;
+    graph.ensure_fingerprints();
+    Ok(graph)
+}
+
+/// A snapshot as a `CodeGraph`, with the store ID of each node by NodeId index
+fn load_graph(
+    store: &dyn GraphStore,
+    snapshot: &str,
+    root_path: &Path,
+) -> Result<(CodeGraph, Vec<StoreNodeId>)> {
     let mut graph = CodeGraph::new(root_path.to_path_buf());
 
     // Load nodes sorted by store ID for deterministic petgraph NodeId assignment
@@ -170,26 +197,82 @@ pub fn reconstruct_graph(
         id_map.insert(*store_id, graph_id);
     }
 
-    // Add edges
-    for (store_id, _) in &nodes {
-        let edges = store.edges_from(*store_id, snapshot)?;
-        for edge_result in edges {
-            if let (Some(&from), Some(&to)) =
-                (id_map.get(&edge_result.from), id_map.get(&edge_result.to))
-            {
-                graph.add_edge(from, to, edge_result.edge);
-            }
+    // Add edges, grouped by source in store ID order
+    let mut edges = store.edges(snapshot)?;
+    edges.sort_by_key(|e| e.from.0);
+    for edge_result in edges {
+        if let (Some(&from), Some(&to)) =
+            (id_map.get(&edge_result.from), id_map.get(&edge_result.to))
+        {
+            graph.add_edge(from, to, edge_result.edge);
         }
     }
 
-    Ok(graph)
+    Ok((graph, nodes.into_iter().map(|(id, _)| id).collect()))
+}
+
+/// `find_changed_nodes` for any store: nodes are paired across snapshots by
+/// [`NodeFingerprint`](crate::graph::NodeFingerprint), and a paired node
+/// counts as changed when its data or line differs.
+///
+/// When either snapshot predates fingerprints, both sides are fingerprinted
+/// afresh so they stay comparable.
+pub(crate) fn changed_nodes(
+    store: &dyn GraphStore,
+    old_snapshot: &str,
+    new_snapshot: &str,
+) -> Result<Vec<(StoreNodeId, Option<StoreNodeId>)>> {
+    // The store does not know the repository root; fingerprints computed
+    // here only need to agree with each other
+    let root = Path::new("");
+    let (mut old, old_ids) = load_graph(store, old_snapshot, root)?;
+    let (mut new, new_ids) = load_graph(store, new_snapshot, root)?;
+    let fingerprinted = |g: &CodeGraph| g.nodes().all(|(_, n)| n.fingerprint().is_some());
+    if !fingerprinted(&old) || !fingerprinted(&new) {
+        old.assign_fingerprints();
+        new.assign_fingerprints();
+    }
+
+    let (old_fps, new_fps) = (old.fingerprints(), new.fingerprints());
+    let pairs = fingerprint::pair(old_fps.iter().enumerate(), new_fps.iter().enumerate());
+
+    Ok(pairs
+        .into_iter()
+        .filter_map(|(n, o)| match o {
+            None => Some((new_ids[n], None)),
+            Some(o) => {
+                let (was, now) = (
+                    &old.inner_graph()[NodeId::new(o)],
+                    &new.inner_graph()[NodeId::new(n)],
+                );
+                (was.data() != now.data() || was.line() != now.line())
+                    .then_some((new_ids[n], Some(old_ids[o])))
+            }
+        })
+        .collect())
 }
 
 /// Create a SQLite-backed CozoStore at `.revet-cache/graph.db` under the given repo root.
 #[cfg(feature = "cozo-store")]
 pub fn create_store(repo_root: &Path) -> Result<CozoStore> {
-    let cache_dir = repo_root.join(".revet-cache");
-    std::fs::create_dir_all(&cache_dir).context("Failed to create .revet-cache directory")?;
-    let db_path = cache_dir.join("graph.db");
-    CozoStore::new_sqlite(&db_path)
+    create_store_at(&StoragePaths::in_repo(repo_root))
+}
+
+/// Create (or open) the SQLite-backed CozoStore in the data directory of
+/// `paths`
+#[cfg(feature = "cozo-store")]
+pub fn create_store_at(paths: &StoragePaths) -> Result<CozoStore> {
+    paths.ensure_data_dir()?;
+    CozoStore::new_sqlite(paths.write_path(GRAPH_DB))
+}
+
+/// Open the existing graph store of `paths`, in the data directory or else
+/// the legacy `.revet-cache/`. `None` when neither has one.
+#[cfg(feature = "cozo-store")]
+pub fn open_store(paths: &StoragePaths) -> Result<Option<CozoStore>> {
+    let db_path = paths.read_path(GRAPH_DB);
+    if !db_path.exists() {
+        return Ok(None);
+    }
+    CozoStore::new_sqlite(&db_path).map(Some)
 }
@@ -0,0 +1,191 @@
+//! Code in suggestions: where it goes and whether it parses
+//!
+//! Analyzers that propose code put it in [`Finding::suggestion_code`] rather
+//! than in the prose. The code is built by string manipulation, so before
+//! output [`verify`] substitutes each snippet into a copy of its file and
+//! parses the result with the file's tree-sitter grammar. A snippet with a
+//! syntax error on the lines it lands on is dropped, leaving the prose, and
+//! counted as demoted.
+
+use crate::finding::{Finding, InsertContext, SuggestedCode};
+use crate::parser::grammar::{language_for, Family, Grammar};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use tree_sitter::Node;
+
+/// The grammar of `path`, if suggestions in it are checked
+fn grammar(path: &Path) -> Option<Grammar> {
+    language_for(path).filter(|g| {
+        matches!(
+            g.family,
+            Family::Python
+                | Family::Js
+                | Family::Go
+                | Family::Ruby
+                | Family::Java
+                | Family::Php
+                | Family::CSharp
+        )
+    })
+}
+
+/// Suggested code for a finding in `path`; `None` for files it can't be
+/// checked in
+pub fn code(
+    path: &Path,
+    snippet: impl Into<String>,
+    insert_context: InsertContext,
+) -> Option<SuggestedCode> {
+    let grammar = grammar(path)?;
+    Some(SuggestedCode {
+        language: grammar.name.to_string(),
+        snippet: snippet.into(),
+        insert_context,
+    })
+}
+
+/// `content` with `code` substituted at the 1-based `line`, and the byte
+/// range of the lines the snippet now covers
+pub fn substitute(
+    content: &str,
+    line: usize,
+    code: &SuggestedCode,
+) -> Option<(String, Range<usize>)> {
+    let start = line_start(content, line)?;
+    let (replaced, snippet) = match &code.insert_context {
+        InsertContext::Line => {
+            let end = content[start..]
+                .find('\n')
+                .map_or(content.len(), |i| start + i);
+            let end = if content[..end].ends_with('\r') {
+                end - 1
+            } else {
+                end
+            };
+            let original = &content[start..end];
+            let indent = &original[..original.len() - original.trim_start().len()];
+            let snippet = code
+                .snippet
+                .lines()
+                .map(|l| format!("{}{}", indent, l))
+                .collect::<Vec<_>>()
+                .join("\n");
+            (start..end, snippet)
+        }
+        InsertContext::Replace { original } => {
+            let at = start + content[start..].find(original.as_str())?;
+            (at..at + original.len(), code.snippet.clone())
+        }
+    };
+
+    let mut substituted = String::with_capacity(content.len() + snippet.len());
+    substituted.push_str(&content[..replaced.start]);
+    substituted.push_str(&snippet);
+    substituted.push_str(&content[replaced.end..]);
+
+    // Widen to whole lines, so errors next to the snippet count
+    let end = replaced.start + snippet.len();
+    let first = substituted[..replaced.start]
+        .rfind('\n')
+        .map_or(0, |i| i + 1);
+    let last = substituted[end..]
+        .find('\n')
+        .map_or(substituted.len(), |i| end + i);
+    Some((substituted, first..last))
+}
+
+/// Byte offset where the 1-based `line` starts
+fn line_start(content: &str, line: usize) -> Option<usize> {
+    if line == 0 {
+        return None;
+    }
+    let mut start = 0;
+    for _ in 1..line {
+        start += content[start..].find('\n')? + 1;
+    }
+    Some(start)
+}
+
+/// Whether `code`, substituted into `content` (the text of `path`) at
+/// `line`, parses without errors on the lines it covers
+pub fn parses(path: &Path, content: &str, line: usize, code: &SuggestedCode) -> bool {
+    let Some(grammar) = grammar(path) else {
+        return false;
+    };
+    let Some((substituted, range)) = substitute(content, line, code) else {
+        return false;
+    };
+    match grammar.parse(&substituted) {
+        Some(tree) => !error_within(tree.root_node(), &range),
+        None => false,
+    }
+}
+
+/// Whether an ERROR or MISSING node under `node` touches `range`
+fn error_within(node: Node, range: &Range<usize>) -> bool {
+    if !node.has_error() || node.end_byte() < range.start || node.start_byte() > range.end {
+        return false;
+    }
+    if node.is_error() || node.is_missing() {
+        return true;
+    }
+    let mut cursor = node.walk();
+    let found = node
+        .children(&mut cursor)
+        .any(|child| error_within(child, range));
+    found
+}
+
+/// Code suggestions a review checked and demoted, by rule
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SuggestionStats {
+    pub checked: BTreeMap<String, usize>,
+    pub demoted: BTreeMap<String, usize>,
+}
+
+impl SuggestionStats {
+    /// Code suggestions demoted to prose, over all rules
+    pub fn demoted_total(&self) -> usize {
+        self.demoted.values().sum()
+    }
+}
+
+/// Check the code suggestion of every finding, dropping the ones that don't
+/// parse in place. Relative finding paths are taken from `repo_root`.
+pub fn verify(findings: &mut [Finding], repo_root: &Path) -> SuggestionStats {
+    let mut stats = SuggestionStats::default();
+    let mut contents: HashMap<PathBuf, Option<String>> = HashMap::new();
+    for finding in findings.iter_mut() {
+        let Some(code) = &finding.suggestion_code else {
+            continue;
+        };
+        let path = repo_root.join(&finding.file);
+        let content = contents
+            .entry(path.clone())
+            .or_insert_with(|| std::fs::read_to_string(&path).ok());
+        let ok = content
+            .as_deref()
+            .is_some_and(|content| parses(&path, content, finding.line, code));
+
+        let rule = rule_of(finding);
+        *stats.checked.entry(rule.clone()).or_default() += 1;
+        if !ok {
+            *stats.demoted.entry(rule).or_default() += 1;
+            finding.suggestion_code = None;
+        }
+    }
+    stats
+}
+
+/// The qualified sub-rule of `finding`, else its ID prefix
+pub fn rule_of(finding: &Finding) -> String {
+    match &finding.rule {
+        Some(rule) => rule.clone(),
+        None => finding
+            .id
+            .rsplit_once('-')
+            .map_or(finding.id.as_str(), |(prefix, _)| prefix)
+            .to_string(),
+    }
+}
@@ -1,40 +1,794 @@
 //! Inline suppression comments — `revet-ignore PREFIX` silences findings at source
+//!
+//! Besides ID prefixes, a comment can name a module or one of its sub-rules:
+//! `// revet-ignore: error_handling:swallowed`. Custom sub-rules can also be
+//! named by their stable ID, prefix and sub-rule: `SEC-custom-acme-live`.
+//!
+//! Options after the rules apply to the rules written since the previous
+//! option: `// revet-ignore: SEC-001 until=2025-06-01 reason="migrating to
+//! vault"`. Once `until` has passed, the rules stop suppressing and the
+//! audit reports the comment as expired.
+//!
+//! Where a comment sits decides what it covers (see [`PLACEMENT_RULES`]):
+//! after code, its own line; on a line of its own, the next line with code;
+//! with `:block` (`// revet-ignore:block CPLX`), the whole function or class
+//! it is above, as the code graph records it. Only comments count — a
+//! `revet-ignore` inside a string literal suppresses nothing.
 
+mod comments;
+
+use crate::analyzer::registry::prefix_of;
+use crate::graph::{CodeGraph, NodeKind};
 use crate::Finding;
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::ops::{Range, RangeInclusive};
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
-static SUPPRESS_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"revet-ignore\s+([\w\-\*]+(?:\s+[\w\-\*]+)*)").unwrap());
+/// One entry of a directive's list: a rule, or a `key=value` option whose
+/// value may be quoted
+const ITEM: &str = r#"[\w\-\*:]+(?:=(?:"[^"]*"|[^\s"]*))?"#;
+
+static SUPPRESS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"revet-ignore(:block)?:?\s+({ITEM}(?:\s+{ITEM})*)"
+    ))
+    .unwrap()
+});
+
+static ITEM_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(ITEM).unwrap());
+
+/// Anything that looks like an attempt at a directive, including
+/// misspellings such as `revet_ignore` or `revet-disable-next-line`
+static DIRECTIVE_LIKE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\brevet[ _\-]?(?:ignore|disable|suppress|skip|noqa)[\w\-]*").unwrap()
+});
+
+/// Block-comment closers that end a directive (`/* revet-ignore SEC */`)
+const COMMENT_CLOSERS: &[&str] = &["*/", "-->"];
+
+/// Part of `line` a directive can read: up to the first comment closer
+fn directive_text(line: &str) -> &str {
+    COMMENT_CLOSERS
+        .iter()
+        .filter_map(|closer| line.find(closer))
+        .min()
+        .map_or(line, |end| &line[..end])
+}
+
+/// Rules of a directive's list and the options after them, with byte
+/// ranges in the list
+struct List<'a> {
+    rules: Vec<(&'a str, usize)>,
+    options: Vec<(RuleOptions, Range<usize>)>,
+    defect: Option<DirectiveDefect>,
+}
+
+fn parse_list(list: &str) -> List<'_> {
+    let mut parsed = List {
+        rules: Vec::new(),
+        options: Vec::new(),
+        defect: None,
+    };
+    let mut group: Option<(RuleOptions, Range<usize>)> = None;
+    for m in ITEM_RE.find_iter(list) {
+        let Some((key, value)) = m.as_str().split_once('=') else {
+            parsed.options.extend(group.take());
+            parsed.rules.push((m.as_str(), m.start()));
+            continue;
+        };
+        let (options, range) = group.get_or_insert_with(|| {
+            let first = parsed.options.last().map_or(0, |(o, _)| o.rules.end);
+            let options = RuleOptions {
+                rules: first..parsed.rules.len(),
+                ..RuleOptions::default()
+            };
+            (options, m.start()..m.end())
+        });
+        range.end = m.end();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(value);
+        match key {
+            "until" if crate::baseline::is_date(value) => options.until = Some(value.to_string()),
+            "until" => {
+                parsed
+                    .defect
+                    .get_or_insert(DirectiveDefect::InvalidDate(value.to_string()));
+            }
+            "reason" => options.reason = Some(value.to_string()),
+            _ => {
+                parsed
+                    .defect
+                    .get_or_insert(DirectiveDefect::UnknownOption(key.to_string()));
+            }
+        }
+    }
+    parsed.options.extend(group);
+    parsed
+}
 
 /// Parse inline suppression comments from file content.
 ///
 /// Returns a map of `line_number → vec_of_prefixes` (1-indexed).
 /// Recognises any comment style (`#`, `//`, `--`, `/*`) — we simply search for
-/// the `revet-ignore` token anywhere on the line.
+/// the `revet-ignore` token anywhere on the line. Rules whose `until` date
+/// has passed are left out.
 pub fn parse_suppressions(content: &str) -> HashMap<usize, Vec<String>> {
+    parse_suppressions_at(content, &crate::baseline::today())
+}
+
+/// [`parse_suppressions`] as of `today` (`YYYY-MM-DD`)
+pub fn parse_suppressions_at(content: &str, today: &str) -> HashMap<usize, Vec<String>> {
     let mut map = HashMap::new();
     for (idx, line) in content.lines().enumerate() {
         let line_no = idx + 1; // 1-indexed
-        if let Some(caps) = SUPPRESS_RE.captures(line) {
-            let prefixes: Vec<String> = caps[1].split_whitespace().map(String::from).collect();
-            map.insert(line_no, prefixes);
+        let Some(start) = line.find("revet-ignore") else {
+            continue;
+        };
+        if let Some(caps) = SUPPRESS_RE.captures(directive_text(&line[start..])) {
+            let list = parse_list(caps.get(2).unwrap().as_str());
+            let prefixes: Vec<String> = list
+                .rules
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| {
+                    !list
+                        .options
+                        .iter()
+                        .any(|(o, _)| o.rules.contains(i) && o.is_expired(today))
+                })
+                .map(|(_, (rule, _))| rule.to_string())
+                .collect();
+            if !prefixes.is_empty() {
+                map.insert(line_no, prefixes);
+            }
         }
     }
     map
 }
 
+/// Why a `revet-ignore` comment, or part of it, has no effect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DirectiveDefect {
+    /// Not spelled `revet-ignore` (`revet_ignore`, `revet-disable-next-line`,
+    /// `Revet-Ignore`, ...), so nothing is suppressed
+    UnknownKeyword(String),
+    /// `revet-ignore:SEC` — without a space after the colon nothing is
+    /// suppressed
+    MissingSpace(String),
+    /// `revet-ignore` followed by no rule
+    NoRules,
+    /// `revet-ignore SEC, SQL` — rules after the separator are ignored
+    Separator {
+        separator: char,
+        ignored: Vec<String>,
+    },
+    /// `until=` is not a `YYYY-MM-DD` date; the rules apply and never expire
+    InvalidDate(String),
+    /// An option other than `until` and `reason`; it is ignored
+    UnknownOption(String),
+}
+
+/// Options after a group of rules: in `revet-ignore SEC SQL
+/// until=2025-06-01 reason="migrating to vault" ML`, SEC and SQL expire
+/// and ML does not
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleOptions {
+    /// Indices into [`Directive::tokens`] of the rules the options follow
+    pub rules: Range<usize>,
+    /// Last day the rules suppress findings, `YYYY-MM-DD`
+    pub until: Option<String>,
+    pub reason: Option<String>,
+    /// 1-based character column where the options start
+    pub column: usize,
+    /// 1-based character column one past the end of the options
+    pub end_column: usize,
+}
+
+impl RuleOptions {
+    /// Whether `until` is before `today` (`YYYY-MM-DD`)
+    pub fn is_expired(&self, today: &str) -> bool {
+        self.until.as_deref().is_some_and(|until| until < today)
+    }
+}
+
+/// One `revet-ignore` comment as written, for auditing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Directive {
+    /// 1-indexed line
+    pub line: usize,
+    /// 1-based character column where the directive starts
+    pub column: usize,
+    /// 1-based character column one past the end of the directive
+    pub end_column: usize,
+    /// Rules the comment suppresses, with their 1-based start columns
+    pub tokens: Vec<(String, usize)>,
+    /// `until=`/`reason=` groups, in the order written
+    pub options: Vec<RuleOptions>,
+    /// Written `revet-ignore:block`
+    pub block: bool,
+    pub defect: Option<DirectiveDefect>,
+}
+
+impl Directive {
+    /// Options of the rule at `index` in `tokens`
+    pub fn options_of(&self, index: usize) -> Option<&RuleOptions> {
+        self.options.iter().find(|o| o.rules.contains(&index))
+    }
+
+    /// Whether the rule at `index` in `tokens` has expired as of `today`
+    pub fn is_expired(&self, index: usize, today: &str) -> bool {
+        self.options_of(index).is_some_and(|o| o.is_expired(today))
+    }
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '*' | ':')
+}
+
+fn char_column(line: &str, byte: usize) -> usize {
+    line[..byte].chars().count() + 1
+}
+
+/// Parse every directive in `content`, malformed ones included.
+///
+/// Unlike [`parse_suppressions`], mentions right after a backtick or quote
+/// (documentation, string literals) are skipped.
+pub fn parse_directives(content: &str) -> Vec<Directive> {
+    let mut directives = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        for m in DIRECTIVE_LIKE_RE.find_iter(line) {
+            if matches!(line[..m.start()].chars().last(), Some('`' | '"' | '\'')) {
+                continue;
+            }
+            directives.push(parse_directive(line, idx + 1, m.start(), m.as_str()));
+        }
+    }
+    directives
+}
+
+/// [`parse_directives`] of `content`, the text of `path`, leaving out the
+/// mentions outside comments
+pub fn parse_directives_in(path: &Path, content: &str) -> Vec<Directive> {
+    let lines = comments::scan(path, content);
+    let texts: Vec<&str> = content.lines().collect();
+    parse_directives(content)
+        .into_iter()
+        .filter(|d| {
+            let text = texts[d.line - 1];
+            let byte = text
+                .char_indices()
+                .nth(d.column - 1)
+                .map_or(text.len(), |(i, _)| i);
+            lines[d.line - 1].comment_at(byte).is_some()
+        })
+        .collect()
+}
+
+fn parse_directive(line: &str, line_no: usize, start: usize, keyword: &str) -> Directive {
+    let column = char_column(line, start);
+    let mut directive = Directive {
+        line: line_no,
+        column,
+        end_column: char_column(line, start + keyword.len()),
+        tokens: Vec::new(),
+        options: Vec::new(),
+        block: false,
+        defect: None,
+    };
+    if keyword != "revet-ignore" {
+        directive.defect = Some(DirectiveDefect::UnknownKeyword(keyword.to_string()));
+        return directive;
+    }
+
+    let text = directive_text(&line[start..]);
+    let Some(caps) = SUPPRESS_RE
+        .captures(text)
+        .filter(|c| c.get(0).unwrap().start() == 0)
+    else {
+        let after = &text[keyword.len()..];
+        // `revet-ignore:block` with nothing after it lists no rules
+        let after = after
+            .strip_prefix(":block")
+            .filter(|rest| !rest.starts_with(is_token_char))
+            .unwrap_or(after);
+        let glued: String = after
+            .strip_prefix(':')
+            .unwrap_or("")
+            .chars()
+            .take_while(|c| is_token_char(*c))
+            .collect();
+        if glued.is_empty() {
+            directive.defect = Some(DirectiveDefect::NoRules);
+        } else {
+            directive.end_column += 1 + glued.chars().count();
+            directive.defect = Some(DirectiveDefect::MissingSpace(glued));
+        }
+        return directive;
+    };
+
+    directive.block = caps.get(1).is_some();
+    let list = caps.get(2).unwrap();
+    let offset = start + list.start();
+    let parsed = parse_list(list.as_str());
+    directive.tokens = parsed
+        .rules
+        .iter()
+        .map(|(rule, at)| (rule.to_string(), char_column(line, offset + at)))
+        .collect();
+    directive.options = parsed
+        .options
+        .into_iter()
+        .map(|(options, range)| RuleOptions {
+            column: char_column(line, offset + range.start),
+            end_column: char_column(line, offset + range.end),
+            ..options
+        })
+        .collect();
+    directive.defect = if directive.tokens.is_empty() {
+        Some(DirectiveDefect::NoRules)
+    } else {
+        parsed.defect
+    };
+    let mut end = start + list.end();
+
+    // `SEC, SQL`: the parser stops at the comma
+    let rest = &text[list.end()..];
+    let trimmed = rest.trim_start();
+    if let Some(separator) = trimmed
+        .chars()
+        .next()
+        .filter(|c| matches!(c, ',' | ';' | '|'))
+    {
+        let tail = &trimmed[separator.len_utf8()..];
+        let ignored_len = tail
+            .char_indices()
+            .find(|(_, c)| {
+                !(is_token_char(*c) || c.is_whitespace() || matches!(c, ',' | ';' | '|'))
+            })
+            .map_or(tail.len(), |(i, _)| i);
+        let ignored: Vec<String> = tail[..ignored_len]
+            .split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '|'))
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect();
+        if !ignored.is_empty() {
+            let tail_start = start + list.end() + (rest.len() - tail.len());
+            end = tail_start + tail[..ignored_len].trim_end().len();
+            directive.defect = Some(DirectiveDefect::Separator { separator, ignored });
+        }
+    }
+    directive.end_column = char_column(line, end);
+    directive
+}
+
+/// Comment openers, longest first, that a directive can sit behind
+const COMMENT_OPENERS: &[&str] = &["<!--", "/**", "///", "//!", "/*", "//", "--", "#"];
+
+/// Edit that removes `token` from the first directive on `line`, or the
+/// whole directive when `token` is `None` or its only rule. Options that
+/// only `token` had go with it.
+///
+/// Returns `(start_column, end_column, removes_line)` in 1-based character
+/// columns; the range is replaced with nothing. A directive that is its
+/// comment's only content takes the comment with it, and `removes_line` is
+/// set when that leaves the line blank.
+pub fn removal_range(line: &str, token: Option<&str>) -> Option<(usize, usize, bool)> {
+    let directive = parse_directives(line)
+        .into_iter()
+        .find(|d| !d.tokens.is_empty())?;
+    let chars: Vec<char> = line.chars().collect();
+
+    if let Some(token) = token.filter(|_| directive.tokens.len() > 1) {
+        let i = directive.tokens.iter().position(|(t, _)| t == token)?;
+        // Rules and options in the order written, as column ranges
+        let mut spans: Vec<(usize, usize)> = directive
+            .tokens
+            .iter()
+            .map(|(t, column)| (*column, column + t.chars().count()))
+            .chain(directive.options.iter().map(|o| (o.column, o.end_column)))
+            .collect();
+        spans.sort_unstable();
+        let first = spans.iter().position(|s| s.0 == directive.tokens[i].1)?;
+        let last = match directive.options_of(i).filter(|o| o.rules.len() == 1) {
+            Some(options) => spans.iter().position(|s| s.0 == options.column)?,
+            None => first,
+        };
+        return Some(if first == 0 {
+            (spans[0].0, spans[last + 1].0, false)
+        } else {
+            (spans[first - 1].1, spans[last].1, false)
+        });
+    }
+
+    let start = directive.column - 1;
+    let end = directive.end_column - 1;
+    let before: String = chars[..start].iter().collect();
+    let after: String = chars[end..].iter().collect();
+    let opener = COMMENT_OPENERS
+        .iter()
+        .find(|o| before.trim_end().ends_with(*o));
+    let after_trimmed = after.trim();
+    let comment_only =
+        opener.is_some() && (after_trimmed.is_empty() || COMMENT_CLOSERS.contains(&after_trimmed));
+
+    if let (Some(opener), true) = (opener, comment_only) {
+        let code = before.trim_end();
+        let code = code[..code.len() - opener.len()].trim_end();
+        if code.is_empty() {
+            return Some((1, chars.len() + 1, true));
+        }
+        return Some((code.chars().count() + 1, chars.len() + 1, false));
+    }
+
+    // Other text shares the comment: drop the directive and one space before it
+    let start = if before.ends_with(' ') {
+        start - 1
+    } else {
+        start
+    };
+    Some((start + 1, end + 1, false))
+}
+
 /// Check whether a finding ID matches any of the given suppression prefixes.
 ///
 /// - `*` matches everything
 /// - `SEC` matches `SEC-001`, `SEC-002`, etc.
 pub fn matches_suppression(finding_id: &str, prefixes: &[String]) -> bool {
-    let finding_prefix = finding_id.split('-').next().unwrap_or(finding_id);
+    let finding_prefix = prefix_of(finding_id);
     prefixes.iter().any(|p| p == "*" || p == finding_prefix)
 }
 
+/// Check whether a finding matches any of the given suppression tokens.
+///
+/// Accepts everything [`matches_suppression`] does, plus the finding's
+/// module (`error_handling`), qualified sub-rule (`error_handling:swallowed`)
+/// or, for custom sub-rules, `PREFIX-custom-<name>` (`SEC-custom-acme-live`).
+pub fn matches_finding(finding: &Finding, tokens: &[String]) -> bool {
+    tokens.iter().any(|t| token_matches(finding, t))
+}
+
+/// Whether the single suppression `token` covers `finding`
+fn token_matches(finding: &Finding, token: &str) -> bool {
+    let prefix = prefix_of(&finding.id);
+    if token == "*" || token == prefix {
+        return true;
+    }
+    let Some(rule) = finding.rule.as_deref() else {
+        return false;
+    };
+    let (module, sub_rule) = rule.split_once(':').unwrap_or((rule, ""));
+    if token == rule || token == module {
+        return true;
+    }
+    sub_rule.starts_with("custom-")
+        && token.strip_prefix(prefix).and_then(|t| t.strip_prefix('-')) == Some(sub_rule)
+}
+
+/// Where a directive sits, which decides the lines it covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// After code on the same line: covers that line
+    Trailing,
+    /// On a line of its own: covers the next line with code, and through
+    /// decorators and attributes the declaration they annotate
+    Standalone,
+    /// `revet-ignore:block` on or above a function or class: covers it to
+    /// its last line
+    Block,
+}
+
+/// How each form of directive is placed and what it covers, for
+/// `revet config check --list-rules`
+pub const PLACEMENT_RULES: &[(&str, &str)] = &[
+    ("code  // revet-ignore SEC", "its own line"),
+    (
+        "// revet-ignore SEC",
+        "the next line with code, skipping blank and comment lines; \
+         above decorators or attributes, also the declaration they annotate",
+    ),
+    (
+        "// revet-ignore:block SEC",
+        "the function or class on or below it, to its last line; \
+         without one, the same as without :block",
+    ),
+];
+
+/// A directive's rules, resolved to the lines they cover
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    /// 1-indexed line of the directive
+    pub line: usize,
+    pub placement: Placement,
+    /// Rules that have not expired
+    pub rules: Vec<String>,
+    /// Lines covered, the directive's own included
+    pub covers: RangeInclusive<usize>,
+    /// Lines of the function or class the directive is on or above, with
+    /// or without `:block`
+    pub construct: Option<RangeInclusive<usize>>,
+}
+
+/// Line ranges of the functions, classes and interfaces of each file, from
+/// the code graph, for `revet-ignore:block`
+#[derive(Debug, Clone, Default)]
+pub struct Constructs {
+    by_file: HashMap<PathBuf, Vec<RangeInclusive<usize>>>,
+}
+
+impl Constructs {
+    pub fn from_graph(graph: &CodeGraph) -> Self {
+        let mut by_file: HashMap<PathBuf, Vec<RangeInclusive<usize>>> = HashMap::new();
+        for (_, node) in graph.nodes() {
+            if !matches!(
+                node.kind(),
+                NodeKind::Function | NodeKind::Class | NodeKind::Interface
+            ) {
+                continue;
+            }
+            if let Some(end) = node.end_line().filter(|end| *end >= node.line()) {
+                by_file
+                    .entry(node.file_path().clone())
+                    .or_default()
+                    .push(node.line()..=end);
+            }
+        }
+        Self { by_file }
+    }
+
+    /// The construct ranges of `file`
+    pub fn of(&self, file: &Path) -> &[RangeInclusive<usize>] {
+        self.by_file.get(file).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Resolve the directives of `content`, the text of `path`, to the lines
+/// they cover; `constructs` are the file's [`Constructs`]. Rules whose
+/// `until` date has passed are left out.
+pub fn resolve_suppressions(
+    path: &Path,
+    content: &str,
+    constructs: &[RangeInclusive<usize>],
+) -> Vec<Suppression> {
+    resolve_suppressions_at(path, content, constructs, &crate::baseline::today())
+}
+
+/// [`resolve_suppressions`] as of `today` (`YYYY-MM-DD`)
+pub fn resolve_suppressions_at(
+    path: &Path,
+    content: &str,
+    constructs: &[RangeInclusive<usize>],
+    today: &str,
+) -> Vec<Suppression> {
+    let source = Source {
+        path,
+        texts: content.lines().collect(),
+        lines: comments::scan(path, content),
+        constructs,
+    };
+    let mut suppressions = Vec::new();
+    for (idx, text) in source.texts.iter().enumerate() {
+        let line = &source.lines[idx];
+        let Some(comment) = text
+            .match_indices("revet-ignore")
+            .find_map(|(start, _)| Some((start, line.comment_at(start)?.end)))
+        else {
+            continue;
+        };
+        let Some(caps) = SUPPRESS_RE.captures(directive_text(&text[comment.0..comment.1])) else {
+            continue;
+        };
+        let list = parse_list(caps.get(2).unwrap().as_str());
+        let rules: Vec<String> = list
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                !list
+                    .options
+                    .iter()
+                    .any(|(o, _)| o.rules.contains(i) && o.is_expired(today))
+            })
+            .map(|(_, (rule, _))| rule.to_string())
+            .collect();
+        if rules.is_empty() {
+            continue;
+        }
+
+        let line_no = idx + 1;
+        // A trailing directive anchors on its own line, a standalone one on
+        // the next line with code
+        let anchor = if line.code {
+            Some(line_no)
+        } else {
+            source.next_code(line_no)
+        };
+        let header = anchor.map(|anchor| source.header(anchor));
+        let construct = header.and_then(|header| {
+            constructs
+                .iter()
+                .filter(|c| *c.start() == header)
+                .max_by_key(|c| *c.end())
+                .cloned()
+        });
+        let (placement, covers) = match (&construct, caps.get(1).is_some()) {
+            (Some(construct), true) => (Placement::Block, line_no..=*construct.end()),
+            _ if line.code => (Placement::Trailing, line_no..=line_no),
+            _ => (Placement::Standalone, line_no..=header.unwrap_or(line_no)),
+        };
+        suppressions.push(Suppression {
+            line: line_no,
+            placement,
+            rules,
+            covers,
+            construct,
+        });
+    }
+    suppressions
+}
+
+/// Decorators spread over more lines than this are not followed to the
+/// declaration they annotate
+const MAX_ATTRIBUTE_LINES: usize = 20;
+
+/// A file's lines, as the comment scanner read them
+struct Source<'a> {
+    path: &'a Path,
+    texts: Vec<&'a str>,
+    lines: Vec<comments::Line>,
+    constructs: &'a [RangeInclusive<usize>],
+}
+
+impl Source<'_> {
+    /// First line after `line` (1-indexed) with code on it
+    fn next_code(&self, line: usize) -> Option<usize> {
+        (line + 1..=self.texts.len()).find(|n| self.lines[n - 1].code)
+    }
+
+    fn is_attribute(&self, line: usize) -> bool {
+        comments::is_attribute(self.path, self.texts[line - 1])
+    }
+
+    /// The declaration `anchor` annotates, if it is a decorator or
+    /// attribute line, else `anchor` itself
+    fn header(&self, anchor: usize) -> usize {
+        // Some parsers start a declaration at its first decorator
+        if !self.is_attribute(anchor) || self.constructs.iter().any(|c| *c.start() == anchor) {
+            return anchor;
+        }
+        let mut header = anchor;
+        while self.is_attribute(header) {
+            match self.next_code(header) {
+                Some(next) => header = next,
+                None => break,
+            }
+        }
+        if self.constructs.iter().any(|c| *c.start() == header) {
+            return header;
+        }
+        // A decorator whose arguments span lines: the nearest declaration
+        // before a blank line
+        let last = (anchor..=self.texts.len().min(anchor + MAX_ATTRIBUTE_LINES))
+            .take_while(|n| !self.texts[n - 1].trim().is_empty())
+            .last()
+            .unwrap_or(anchor);
+        self.constructs
+            .iter()
+            .map(|c| *c.start())
+            .filter(|start| (anchor + 1..=last).contains(start))
+            .min()
+            .unwrap_or(header)
+    }
+}
+
+/// A finding a directive's rule would have matched, placed elsewhere
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Misplaced {
+    /// Line of the nearest such finding
+    pub line: usize,
+    /// Whether `:block` on the directive would cover it
+    pub block: bool,
+}
+
+/// Findings this many lines from what a directive covers are taken as
+/// meant for it
+const NEAR_LINES: usize = 3;
+
+/// Which rule tokens of inline directives suppressed at least one finding
+#[derive(Debug, Clone, Default)]
+pub struct DirectiveUsage {
+    used: HashMap<PathBuf, HashMap<usize, HashSet<String>>>,
+    misplaced: HashMap<PathBuf, HashMap<(usize, String), Misplaced>>,
+}
+
+impl DirectiveUsage {
+    /// Whether `token` of the directive on `line` of `file` suppressed a finding
+    pub fn is_used(&self, file: &Path, line: usize, token: &str) -> bool {
+        self.used
+            .get(file)
+            .and_then(|lines| lines.get(&line))
+            .is_some_and(|tokens| tokens.contains(token))
+    }
+
+    /// A kept finding `token` of the directive on `line` of `file` would
+    /// have matched, had the directive been placed differently
+    pub fn misplaced(&self, file: &Path, line: usize, token: &str) -> Option<Misplaced> {
+        self.misplaced
+            .get(file)
+            .and_then(|lines| lines.get(&(line, token.to_string())))
+            .copied()
+    }
+
+    fn record(&mut self, finding: &Finding, line: usize, tokens: &[String]) {
+        for token in tokens.iter().filter(|t| token_matches(finding, t)) {
+            self.used
+                .entry(finding.file.clone())
+                .or_default()
+                .entry(line)
+                .or_default()
+                .insert(token.clone());
+        }
+    }
+}
+
+/// `content` with a `revet-ignore <rule> — <reason>` comment inserted above
+/// each `(line, rule, reason)` (1-indexed), indented like the line it
+/// silences. The em dash ends the rule list, so the reason is not read as
+/// rules. Directives for the same line share one comment.
+pub fn insert_directives(
+    path: &Path,
+    content: &str,
+    directives: &[(usize, String, String)],
+) -> String {
+    let mut by_line: std::collections::BTreeMap<usize, (Vec<&str>, Vec<&str>)> =
+        std::collections::BTreeMap::new();
+    for (line, rule, reason) in directives {
+        let (rules, reasons) = by_line.entry(*line).or_default();
+        if !rules.contains(&rule.as_str()) {
+            rules.push(rule);
+        }
+        if !reasons.contains(&reason.as_str()) {
+            reasons.push(reason);
+        }
+    }
+
+    let prefix = crate::fixer::comment_prefix(path);
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    for (&line, (rules, reasons)) in by_line.iter().rev() {
+        let Some(target) = line.checked_sub(1).and_then(|i| lines.get(i)) else {
+            continue;
+        };
+        let indent: String = target.chars().take_while(|c| c.is_whitespace()).collect();
+        let comment = format!(
+            "{}{} revet-ignore {} — {}",
+            indent,
+            prefix,
+            rules.join(" "),
+            reasons.join("; ")
+        );
+        lines.insert(line - 1, comment);
+    }
+
+    let mut output = lines.join("\n");
+    if content.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
+
+/// Result of a suppression pass: the findings left, the ones suppressed,
+/// and which directives did the suppressing
+#[derive(Debug, Default)]
+pub struct FilterOutcome {
+    pub kept: Vec<Finding>,
+    pub suppressed: Vec<SuppressedFinding>,
+    pub usage: DirectiveUsage,
+}
+
 /// A finding that was suppressed, paired with the reason for suppression.
 #[derive(Debug, Clone)]
 pub struct SuppressedFinding {
@@ -45,71 +799,105 @@ pub struct SuppressedFinding {
 
 /// Filter findings by inline `revet-ignore` comments in source files.
 ///
-/// For each finding at line N, checks for suppression comments at line N
-/// (same-line) and line N-1 (line-before).
+/// A finding is suppressed by a directive that covers its line (see
+/// [`Placement`]) and names its rule.
 ///
 /// Returns `(kept_findings, suppressed)`.
 pub fn filter_findings_by_inline(findings: Vec<Finding>) -> (Vec<Finding>, Vec<SuppressedFinding>) {
+    let outcome = filter_inline(findings);
+    (outcome.kept, outcome.suppressed)
+}
+
+/// Like [`filter_findings_by_inline`], also recording which directive
+/// tokens suppressed a finding (see [`DirectiveUsage`]). Without a code
+/// graph, `revet-ignore:block` covers what a plain directive would.
+pub fn filter_inline(findings: Vec<Finding>) -> FilterOutcome {
+    filter_inline_with(findings, &Constructs::default())
+}
+
+/// [`filter_inline`] with the functions and classes `revet-ignore:block`
+/// covers
+pub fn filter_inline_with(findings: Vec<Finding>, constructs: &Constructs) -> FilterOutcome {
     // Group findings by file to read each file only once
-    let mut by_file: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut by_file: HashMap<PathBuf, Vec<usize>> = HashMap::new();
     for (i, f) in findings.iter().enumerate() {
-        let key = f.file.to_string_lossy().into_owned();
-        by_file.entry(key).or_default().push(i);
+        by_file.entry(f.file.clone()).or_default().push(i);
     }
 
-    // Parse suppressions for each unique file
-    let mut file_suppressions: HashMap<String, HashMap<usize, Vec<String>>> = HashMap::new();
+    // Resolve the suppressions of each unique file
+    let mut file_suppressions: HashMap<PathBuf, Vec<Suppression>> = HashMap::new();
     for file_path in by_file.keys() {
         if let Ok(content) = fs::read_to_string(file_path) {
-            let sups = parse_suppressions(&content);
+            let sups = resolve_suppressions(file_path, &content, constructs.of(file_path));
             if !sups.is_empty() {
                 file_suppressions.insert(file_path.clone(), sups);
             }
         }
     }
 
-    let mut kept = Vec::new();
-    let mut suppressed: Vec<SuppressedFinding> = Vec::new();
+    let mut outcome = FilterOutcome::default();
 
     for finding in findings {
-        let key = finding.file.to_string_lossy().into_owned();
-        let is_suppressed = if let Some(sups) = file_suppressions.get(&key) {
-            let line = finding.line;
-            // Check same-line
-            let same_line = sups
-                .get(&line)
-                .map(|p| matches_suppression(&finding.id, p))
-                .unwrap_or(false);
-            // Check line-before (only if line > 1)
-            let line_before = if line > 1 {
-                sups.get(&(line - 1))
-                    .map(|p| matches_suppression(&finding.id, p))
-                    .unwrap_or(false)
-            } else {
-                false
-            };
-            same_line || line_before
-        } else {
-            false
-        };
+        let mut is_suppressed = false;
+        for sup in file_suppressions.get(&finding.file).into_iter().flatten() {
+            if sup.covers.contains(&finding.line) && matches_finding(&finding, &sup.rules) {
+                outcome.usage.record(&finding, sup.line, &sup.rules);
+                is_suppressed = true;
+            }
+        }
 
         if is_suppressed {
-            suppressed.push(SuppressedFinding {
+            outcome.suppressed.push(SuppressedFinding {
                 finding,
                 reason: "inline".to_string(),
             });
         } else {
-            kept.push(finding);
+            outcome.kept.push(finding);
         }
     }
 
-    (kept, suppressed)
+    // Rules that matched a kept finding near them were likely misplaced
+    for finding in &outcome.kept {
+        for sup in file_suppressions.get(&finding.file).into_iter().flatten() {
+            let in_construct = sup.placement != Placement::Block
+                && sup
+                    .construct
+                    .as_ref()
+                    .is_some_and(|c| c.contains(&finding.line));
+            let near = finding.line + NEAR_LINES >= sup.line
+                && finding.line <= sup.covers.end() + NEAR_LINES;
+            if !(in_construct || near) {
+                continue;
+            }
+            for token in sup.rules.iter().filter(|t| token_matches(finding, t)) {
+                let misplaced = outcome
+                    .usage
+                    .misplaced
+                    .entry(finding.file.clone())
+                    .or_default()
+                    .entry((sup.line, token.clone()))
+                    .or_insert(Misplaced {
+                        line: finding.line,
+                        block: in_construct,
+                    });
+                if finding.line.abs_diff(sup.line) < misplaced.line.abs_diff(sup.line) {
+                    *misplaced = Misplaced {
+                        line: finding.line,
+                        block: in_construct,
+                    };
+                }
+            }
+        }
+    }
+
+    outcome
 }
 
 /// Filter findings using per-path suppression rules from `.revet.toml`.
 ///
 /// `per_path` maps glob patterns (e.g. `"**/tests/**"`) to lists of finding
-/// ID prefixes (e.g. `["SEC", "SQL"]` or `["*"]` for all).
+/// ID prefixes (e.g. `["SEC", "SQL"]` or `["*"]` for all). Repository-level
+/// findings have no path to match and are always kept.
 ///
 /// Returns `(kept_findings, suppressed)`.
 pub fn filter_findings_by_path_rules(
@@ -135,6 +923,11 @@ pub fn filter_findings_by_path_rules(
     let mut suppressed: Vec<SuppressedFinding> = Vec::new();
 
     for finding in findings {
+        if finding.is_repository_level() {
+            kept.push(finding);
+            continue;
+        }
+
         // Match against the path relative to repo root for consistent glob behaviour
         let rel_path = finding
             .file
@@ -143,7 +936,7 @@ pub fn filter_findings_by_path_rules(
         let path_str = rel_path.to_string_lossy();
 
         let matched = rules.iter().find(|(pattern, _, prefixes)| {
-            pattern.matches(&path_str) && matches_suppression(&finding.id, prefixes)
+            pattern.matches(&path_str) && matches_finding(&finding, prefixes)
         });
 
         if let Some((_, pattern_str, _)) = matched {
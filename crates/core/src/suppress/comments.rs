@@ -0,0 +1,494 @@
+//! Where the comments are, per language — so a `revet-ignore` is only read
+//! from a comment, and one after code is told apart from one on a line of
+//! its own
+//!
+//! A small tokenizer rather than a substring search: `"http://host"` holds
+//! no comment, nor does `f"{a}#{b}"` or `/\/\//`, and a `/* ... */` may
+//! span lines. Files of unknown languages fall back to the first comment
+//! opener on the line.
+
+use std::ops::Range;
+use std::path::Path;
+
+/// What a line of source holds
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Line {
+    /// Whether anything but comments and whitespace is on the line; string
+    /// contents count as code
+    pub code: bool,
+    /// Byte ranges of the comments on the line, openers included
+    pub comments: Vec<Range<usize>>,
+}
+
+impl Line {
+    /// The comment byte `at` is in
+    pub fn comment_at(&self, at: usize) -> Option<&Range<usize>> {
+        self.comments.iter().find(|c| c.contains(&at))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lang {
+    /// C, C++, C#, PHP, Dart, protobuf
+    C,
+    /// C syntax plus `"""` text blocks: Java, Kotlin, Scala, Swift, Groovy
+    Java,
+    Rust,
+    Go,
+    /// JavaScript and TypeScript
+    Script,
+    Python,
+    /// `#` comments after whitespace: shell, Ruby, YAML, TOML, R, Perl, ...
+    Shell,
+    /// Terraform and HCL
+    Hcl,
+    Sql,
+    /// HTML, XML, Markdown
+    Markup,
+    /// Anything else
+    Plain,
+}
+
+impl Lang {
+    fn of(path: &Path) -> Self {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if matches!(
+            name,
+            "Dockerfile" | "Containerfile" | "Makefile" | "Gemfile" | "Rakefile"
+        ) || name.starts_with("Dockerfile.")
+        {
+            return Lang::Shell;
+        }
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match ext.as_str() {
+            "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "cs" | "php" | "dart" | "proto" => {
+                Lang::C
+            }
+            "java" | "kt" | "kts" | "scala" | "swift" | "groovy" | "gradle" => Lang::Java,
+            "rs" => Lang::Rust,
+            "go" => Lang::Go,
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => Lang::Script,
+            "py" | "pyi" | "pyw" => Lang::Python,
+            "sh" | "bash" | "zsh" | "rb" | "yaml" | "yml" | "toml" | "r" | "pl" | "pm" | "ex"
+            | "exs" | "cmake" | "ps1" | "mk" => Lang::Shell,
+            "tf" | "tfvars" | "hcl" => Lang::Hcl,
+            "sql" => Lang::Sql,
+            "html" | "htm" | "xml" | "xhtml" | "svg" | "md" | "markdown" => Lang::Markup,
+            _ => Lang::Plain,
+        }
+    }
+
+    fn line_openers(self) -> &'static [&'static str] {
+        match self {
+            Lang::C | Lang::Java | Lang::Rust | Lang::Go | Lang::Script => &["//"],
+            Lang::Python | Lang::Shell => &["#"],
+            Lang::Hcl => &["#", "//"],
+            Lang::Sql => &["--"],
+            Lang::Markup | Lang::Plain => &[],
+        }
+    }
+
+    fn block(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            Lang::Python | Lang::Shell | Lang::Plain => None,
+            Lang::Markup => Some(("<!--", "-->")),
+            _ => Some(("/*", "*/")),
+        }
+    }
+}
+
+/// Whether `text`, a line of `path`, opens with a decorator or attribute
+/// (`@app.route`, `#[test]`, `[Fact]`) and so annotates the line below
+pub(crate) fn is_attribute(path: &Path, text: &str) -> bool {
+    let text = text.trim_start();
+    match Lang::of(path) {
+        Lang::Rust => text.starts_with("#["),
+        Lang::Python | Lang::Java | Lang::Script => text.starts_with('@'),
+        Lang::C => text.starts_with('@') || text.starts_with('[') || text.starts_with("#["),
+        _ => false,
+    }
+}
+
+/// Scan `content`, the text of `path`, into one [`Line`] per line
+pub(crate) fn scan(path: &Path, content: &str) -> Vec<Line> {
+    let lang = Lang::of(path);
+    if lang == Lang::Plain {
+        return content.split('\n').map(plain).collect();
+    }
+    let mut scanner = Scanner {
+        lang,
+        src: content,
+        bytes: content.as_bytes(),
+        pos: 0,
+        line: 0,
+        line_start: 0,
+        lines: vec![Line::default(); content.split('\n').count()],
+        stack: vec![Frame::Code { braces: 0 }],
+    };
+    scanner.run();
+    scanner.lines
+}
+
+/// The legacy reading for unknown languages: the comment starts at the
+/// first opener, or at the directive itself
+fn plain(text: &str) -> Line {
+    const OPENERS: &[&str] = &["<!--", "/*", "//", "--", "#"];
+    let start = OPENERS
+        .iter()
+        .filter_map(|o| text.find(o))
+        .chain(text.to_ascii_lowercase().find("revet"))
+        .min();
+    match start {
+        Some(start) => Line {
+            code: !text[..start].trim().is_empty(),
+            comments: std::iter::once(start..text.len()).collect(),
+        },
+        None => Line {
+            code: !text.trim().is_empty(),
+            comments: Vec::new(),
+        },
+    }
+}
+
+/// `${...}` in JavaScript templates and HCL strings, `{...}` in Python
+/// f-strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Interpolation {
+    Dollar,
+    Brace,
+}
+
+#[derive(Debug, Clone)]
+enum Frame {
+    /// Code; inside an interpolation, with the braces open in it
+    Code { braces: usize },
+    Str {
+        close: String,
+        escapes: bool,
+        multiline: bool,
+        interpolation: Option<Interpolation>,
+    },
+}
+
+struct Scanner<'a> {
+    lang: Lang,
+    src: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    /// 0-based line of `pos`
+    line: usize,
+    line_start: usize,
+    lines: Vec<Line>,
+    stack: Vec<Frame>,
+}
+
+impl Scanner<'_> {
+    fn run(&mut self) {
+        while self.pos < self.bytes.len() {
+            if self.bytes[self.pos] == b'\n' {
+                // Strings that cannot span lines end unterminated
+                if let Some(Frame::Str {
+                    multiline: false, ..
+                }) = self.stack.last()
+                {
+                    self.stack.pop();
+                }
+                self.skip(1);
+                continue;
+            }
+            match self.stack.last() {
+                Some(Frame::Str { .. }) => self.string(),
+                _ => self.code(),
+            }
+        }
+    }
+
+    fn at(&self, s: &str) -> bool {
+        self.bytes[self.pos..].starts_with(s.as_bytes())
+    }
+
+    fn skip(&mut self, n: usize) {
+        for _ in 0..n {
+            if self.pos >= self.bytes.len() {
+                return;
+            }
+            if self.bytes[self.pos] == b'\n' {
+                self.line += 1;
+                self.line_start = self.pos + 1;
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Mark the line as code if the byte at `pos` is not whitespace
+    fn mark(&mut self) {
+        if !self.bytes[self.pos].is_ascii_whitespace() {
+            self.lines[self.line].code = true;
+        }
+    }
+
+    /// Consume a comment of `open` bytes, up to `close` or, for line
+    /// comments, the end of the line
+    fn comment(&mut self, open: usize, close: Option<&str>) {
+        let mut start = self.pos;
+        self.skip(open);
+        loop {
+            if self.pos >= self.bytes.len() {
+                break;
+            }
+            if self.bytes[self.pos] == b'\n' {
+                self.push_comment(start);
+                if close.is_none() {
+                    return;
+                }
+                self.skip(1);
+                start = self.pos;
+                continue;
+            }
+            if let Some(close) = close.filter(|c| self.at(c)) {
+                self.skip(close.len());
+                break;
+            }
+            self.skip(1);
+        }
+        self.push_comment(start);
+    }
+
+    fn push_comment(&mut self, start: usize) {
+        let range = start - self.line_start..self.pos - self.line_start;
+        if !range.is_empty() {
+            self.lines[self.line].comments.push(range);
+        }
+    }
+
+    fn in_interpolation(&self) -> bool {
+        self.stack.len() > 1
+    }
+
+    /// Whether the byte before `pos` on this line is whitespace, or there
+    /// is none
+    fn after_space(&self) -> bool {
+        self.pos == self.line_start || self.bytes[self.pos - 1].is_ascii_whitespace()
+    }
+
+    fn code(&mut self) {
+        let lang = self.lang;
+        if let Some((open, close)) = lang.block().filter(|(open, _)| self.at(open)) {
+            self.comment(open.len(), Some(close));
+            return;
+        }
+        // Comments are not allowed inside a Python f-string's braces, so a
+        // `#` there is a format spec (`{n:#x}`)
+        let python_braces = lang == Lang::Python && self.in_interpolation();
+        if let Some(open) = lang.line_openers().iter().find(|o| self.at(o)) {
+            if !python_braces && (lang != Lang::Shell || self.after_space()) {
+                self.comment(open.len(), None);
+                return;
+            }
+        }
+
+        let b = self.bytes[self.pos];
+        let nested = self.in_interpolation();
+        if let Some(Frame::Code { braces }) = self.stack.last_mut().filter(|_| nested) {
+            match b {
+                b'{' => *braces += 1,
+                b'}' if *braces == 0 => {
+                    self.stack.pop();
+                    self.skip(1);
+                    return;
+                }
+                b'}' => *braces -= 1,
+                _ => {}
+            }
+        }
+        if let Some((frame, open)) = self.open_string() {
+            self.lines[self.line].code = true;
+            self.skip(open);
+            self.stack.push(frame);
+            return;
+        }
+        if b == b'\'' && lang == Lang::Rust {
+            self.rust_quote();
+        } else if b == b'/' && lang == Lang::Script && self.starts_regex() {
+            self.regex();
+        } else {
+            self.mark();
+            self.skip(1);
+        }
+    }
+
+    /// The string opening at `pos`, if any, and the length of its opener
+    fn open_string(&self) -> Option<(Frame, usize)> {
+        use Interpolation::{Brace, Dollar};
+        let b = self.bytes[self.pos];
+        let quote = if b == b'"' { "\"" } else { "'" };
+        let frame = |close: &str, escapes: bool, multiline: bool, interpolation| Frame::Str {
+            close: close.to_string(),
+            escapes,
+            multiline,
+            interpolation,
+        };
+        match (self.lang, b) {
+            (Lang::Java, b'"') if self.at("\"\"\"") => Some((frame("\"\"\"", true, true, None), 3)),
+            (Lang::C | Lang::Java | Lang::Go | Lang::Script, b'"' | b'\'') => {
+                Some((frame(quote, true, false, None), 1))
+            }
+            (Lang::Go, b'`') => Some((frame("`", false, true, None), 1)),
+            (Lang::Script, b'`') => Some((frame("`", true, true, Some(Dollar)), 1)),
+            (Lang::Rust, b'"') => {
+                // `r#"..."#`: the hashes before the quote close it too
+                let before = &self.bytes[..self.pos];
+                let hashes = before.iter().rev().take_while(|&&c| c == b'#').count();
+                let prefix = &before[..before.len() - hashes];
+                let raw = prefix.ends_with(b"r") && {
+                    let rest = &prefix[..prefix.len() - 1];
+                    let rest = rest.strip_suffix(b"b").unwrap_or(rest);
+                    !rest.last().is_some_and(|c| is_ident(*c))
+                };
+                if raw {
+                    let close = format!("\"{}", "#".repeat(hashes));
+                    Some((frame(&close, false, true, None), 1))
+                } else {
+                    Some((frame(quote, true, true, None), 1))
+                }
+            }
+            (Lang::Python, b'"' | b'\'') => {
+                let prefix: Vec<u8> = self.bytes[self.line_start..self.pos]
+                    .iter()
+                    .rev()
+                    .take_while(|c| is_ident(**c))
+                    .map(|c| c.to_ascii_lowercase())
+                    .collect();
+                let known = prefix.len() <= 2 && prefix.iter().all(|c| b"rbuf".contains(c));
+                let raw = known && prefix.contains(&b'r');
+                let formatted = (known && prefix.contains(&b'f')).then_some(Brace);
+                let triple = quote.repeat(3);
+                if self.at(&triple) {
+                    Some((frame(&triple, !raw, true, formatted), 3))
+                } else {
+                    Some((frame(quote, !raw, false, formatted), 1))
+                }
+            }
+            (Lang::Shell, b'"' | b'\'') => {
+                // `don't` in a YAML scalar or a shell word opens no string
+                let opens = self.pos == self.line_start
+                    || matches!(
+                        self.bytes[self.pos - 1],
+                        b' ' | b'\t' | b'=' | b':' | b'(' | b'[' | b'{' | b',' | b'$'
+                    );
+                opens.then(|| (frame(quote, b == b'"', false, None), 1))
+            }
+            (Lang::Hcl, b'"') => Some((frame(quote, true, false, Some(Dollar)), 1)),
+            (Lang::Sql, b'\'' | b'"') => Some((frame(quote, false, true, None), 1)),
+            _ => None,
+        }
+    }
+
+    fn string(&mut self) {
+        let Some(Frame::Str {
+            close,
+            escapes,
+            interpolation,
+            ..
+        }) = self.stack.last()
+        else {
+            return;
+        };
+        let (close, escapes, interpolation) = (close.clone(), *escapes, *interpolation);
+        self.mark();
+        if self.at(&close) {
+            self.skip(close.len());
+            self.stack.pop();
+            return;
+        }
+        let b = self.bytes[self.pos];
+        if escapes && b == b'\\' {
+            self.skip(2);
+            return;
+        }
+        match interpolation {
+            Some(Interpolation::Dollar) if self.at("${") => {
+                self.skip(2);
+                self.stack.push(Frame::Code { braces: 0 });
+            }
+            Some(Interpolation::Brace) if self.at("{{") => self.skip(2),
+            Some(Interpolation::Brace) if b == b'{' => {
+                self.skip(1);
+                self.stack.push(Frame::Code { braces: 0 });
+            }
+            _ => self.skip(1),
+        }
+    }
+
+    /// `'a'` and `'\n'` are characters; `'a` in `&'a str` is a lifetime
+    fn rust_quote(&mut self) {
+        self.lines[self.line].code = true;
+        let mut rest = self.src[self.pos + 1..].chars();
+        match rest.next() {
+            Some('\\') => {
+                self.skip(1);
+                self.stack.push(Frame::Str {
+                    close: "'".to_string(),
+                    escapes: true,
+                    multiline: false,
+                    interpolation: None,
+                });
+            }
+            Some(c) if c != '\n' && rest.next() == Some('\'') => self.skip(2 + c.len_utf8()),
+            _ => self.skip(1),
+        }
+    }
+
+    /// Whether the `/` at `pos` opens a regular expression rather than
+    /// dividing: it follows an operator, an opening bracket or a keyword
+    fn starts_regex(&self) -> bool {
+        let before = self.src[self.line_start..self.pos].trim_end();
+        let Some(last) = before.bytes().last() else {
+            return true;
+        };
+        if b"(,=:[!&|?{};+-*%<>~^".contains(&last) {
+            return true;
+        }
+        let word = before
+            .rsplit(|c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '$')
+            .next()
+            .unwrap_or("");
+        matches!(
+            word,
+            "return" | "typeof" | "case" | "yield" | "await" | "void" | "delete" | "in" | "of"
+        )
+    }
+
+    fn regex(&mut self) {
+        self.lines[self.line].code = true;
+        self.skip(1);
+        let mut class = false;
+        while self.pos < self.bytes.len() {
+            match self.bytes[self.pos] {
+                b'\n' => return,
+                b'\\' if self.bytes.get(self.pos + 1) != Some(&b'\n') => self.skip(2),
+                b'[' => {
+                    class = true;
+                    self.skip(1);
+                }
+                b']' => {
+                    class = false;
+                    self.skip(1);
+                }
+                b'/' if !class => {
+                    self.skip(1);
+                    return;
+                }
+                _ => self.skip(1),
+            }
+        }
+    }
+}
+
+fn is_ident(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_'
+}
@@ -0,0 +1,104 @@
+//! Structured trace of analyzer decisions (`revet debug replay --trace`)
+//!
+//! Analyzers call [`record`] where they decide to report, skip or drop
+//! something. Nothing is kept unless [`collect`] is running on the current
+//! thread, so in a normal review a call costs one thread-local check and the
+//! detail text is never built.
+
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
+/// What an analyzer decided at a traced point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Decision {
+    /// A pattern or check matched
+    Matched,
+    /// A candidate was deliberately not reported
+    Skipped,
+    /// A finding was changed before reporting (e.g. its severity)
+    Adjusted,
+    /// The dispatcher dropped a finding (sub-rule selection, `[ignore]`)
+    Filtered,
+    /// The dispatcher kept a finding under its final ID
+    Reported,
+}
+
+impl std::fmt::Display for Decision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Decision::Matched => write!(f, "matched"),
+            Decision::Skipped => write!(f, "skipped"),
+            Decision::Adjusted => write!(f, "adjusted"),
+            Decision::Filtered => write!(f, "filtered"),
+            Decision::Reported => write!(f, "reported"),
+        }
+    }
+}
+
+/// One traced decision
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceEvent {
+    /// Finding prefix of the analyzer being traced
+    pub analyzer: String,
+    pub decision: Decision,
+    pub file: PathBuf,
+    /// 1-based line the decision is about; 0 for the whole file
+    pub line: usize,
+    pub detail: String,
+}
+
+struct Collector {
+    analyzer: String,
+    events: Vec<TraceEvent>,
+}
+
+thread_local! {
+    static COLLECTOR: RefCell<Option<Collector>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with tracing on for this thread, attributing events to the
+/// analyzer with prefix `analyzer`. Returns `f`'s result and the events in
+/// the order they were recorded. Nested calls collect into the outer trace.
+pub fn collect<T>(analyzer: &str, f: impl FnOnce() -> T) -> (T, Vec<TraceEvent>) {
+    let outer = COLLECTOR.with(|c| {
+        c.borrow_mut().replace(Collector {
+            analyzer: analyzer.to_string(),
+            events: Vec::new(),
+        })
+    });
+    let result = f();
+    let events = COLLECTOR.with(|c| {
+        let mut slot = c.borrow_mut();
+        let events = slot.take().map(|c| c.events).unwrap_or_default();
+        *slot = outer;
+        if let Some(outer) = slot.as_mut() {
+            outer.events.extend(events.iter().cloned());
+        }
+        events
+    });
+    (result, events)
+}
+
+/// Whether a trace is being collected on this thread
+pub fn enabled() -> bool {
+    COLLECTOR.with(|c| c.borrow().is_some())
+}
+
+/// Record a decision about `line` of `file`. `detail` is only called while
+/// a trace is being collected.
+pub fn record(file: &Path, line: usize, decision: Decision, detail: impl FnOnce() -> String) {
+    COLLECTOR.with(|c| {
+        if let Some(collector) = c.borrow_mut().as_mut() {
+            let event = TraceEvent {
+                analyzer: collector.analyzer.clone(),
+                decision,
+                file: file.to_path_buf(),
+                line,
+                detail: detail(),
+            };
+            collector.events.push(event);
+        }
+    });
+}
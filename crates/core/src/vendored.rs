@@ -0,0 +1,344 @@
+//! Vendored third-party code with local patches — `[[vendored]]`
+//!
+//! A vendored library is thousands of lines nobody in the repository
+//! maintains, plus the handful of lines patched locally. Each `[[vendored]]`
+//! entry names the directory and the `upstream_ref` it was vendored from;
+//! the directory's files are compared with that upstream tree and findings
+//! under the directory are kept only on the lines that differ — the same
+//! filtering `revet diff` applies, but in every review mode:
+//!
+//! - files matching upstream are not analyzed, and are recorded in the
+//!   coverage ledger as [`SkipReason::VendoredUpstream`]
+//! - in patched files, findings on upstream lines are dropped and counted
+//! - files added locally keep all their findings
+//!
+//! When `upstream_ref` is missing or does not resolve, the directory falls
+//! back to a generated-file policy ([`GeneratedPolicy`]): the entry's
+//! `fallback`, or `[files] generated`.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use git2::{ObjectType, Oid, Patch, Repository, Tree};
+
+use crate::config::{RevetConfig, VendoredConfig};
+use crate::diff::DiffFileLines;
+use crate::generated::GeneratedPolicy;
+use crate::{CoverageLedger, Finding, Severity, SkipReason};
+
+/// One `[[vendored]]` directory and what it was compared with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VendoredOverlay {
+    /// Directory relative to the repository root
+    pub path: PathBuf,
+    pub upstream_ref: Option<String>,
+    /// Upstream tree of the directory, once `upstream_ref` resolved
+    pub tree: Option<String>,
+    /// Why `upstream_ref` did not resolve; `None` without one
+    pub error: Option<String>,
+    /// Policy for the directory when there is no upstream tree
+    pub fallback: GeneratedPolicy,
+}
+
+impl VendoredOverlay {
+    fn contains(&self, relative: &Path) -> bool {
+        relative.starts_with(&self.path)
+    }
+}
+
+/// The `[[vendored]]` directories of a repository, resolved against its
+/// git objects
+#[derive(Debug, Clone, Default)]
+pub struct VendoredFiles {
+    repo_root: PathBuf,
+    overlays: Vec<VendoredOverlay>,
+}
+
+impl VendoredFiles {
+    /// Resolve each entry's `upstream_ref` in the repository at
+    /// `repo_root`. An invalid `fallback` is reported by
+    /// [`RevetConfig::validate`]; here it falls back to `[files] generated`.
+    pub fn resolve(config: &RevetConfig, repo_root: &Path) -> Self {
+        if config.vendored.is_empty() {
+            return Self::default();
+        }
+        let repo_root = repo_root.to_path_buf();
+        let default_fallback =
+            GeneratedPolicy::parse(&config.files.generated).unwrap_or(GeneratedPolicy::Skip);
+        let repo = Repository::open(&repo_root);
+        let overlays = config
+            .vendored
+            .iter()
+            .filter_map(|entry| {
+                // An empty path is reported by validation; it would cover
+                // the whole repository
+                let path = normalize(&entry.path);
+                if path.as_os_str().is_empty() {
+                    return None;
+                }
+                let fallback = entry
+                    .fallback
+                    .as_deref()
+                    .and_then(GeneratedPolicy::parse)
+                    .unwrap_or(default_fallback);
+                let resolved = entry.upstream_ref.as_deref().map(|spec| match &repo {
+                    Ok(repo) => upstream_tree(repo, spec, &path).map_err(|e| format!("{:#}", e)),
+                    Err(e) => Err(format!("not a git repository: {}", e.message())),
+                });
+                let (tree, error) = match resolved {
+                    Some(Ok(tree)) => (Some(tree.to_string()), None),
+                    Some(Err(e)) => (None, Some(e)),
+                    None => (None, None),
+                };
+                Some(VendoredOverlay {
+                    path,
+                    upstream_ref: entry.upstream_ref.clone(),
+                    tree,
+                    error,
+                    fallback,
+                })
+            })
+            .collect();
+        Self {
+            repo_root,
+            overlays,
+        }
+    }
+
+    pub fn overlays(&self) -> &[VendoredOverlay] {
+        &self.overlays
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overlays.is_empty()
+    }
+
+    /// Compare every file of `files` under a vendored directory with its
+    /// upstream version. `root` is where the files are: the repository, or
+    /// a checkout of one of its commits.
+    pub fn classify(&self, files: &[PathBuf], root: &Path) -> VendoredSet {
+        let mut set = VendoredSet::default();
+        if self.overlays.is_empty() {
+            return set;
+        }
+        let repo = Repository::open(&self.repo_root).ok();
+        let mut trees: HashMap<&str, Option<Tree<'_>>> = HashMap::new();
+        for file in files {
+            let relative = file.strip_prefix(root).unwrap_or(file);
+            let Some(overlay) = self.overlays.iter().find(|o| o.contains(relative)) else {
+                continue;
+            };
+            let tree = overlay.tree.as_deref().and_then(|id| {
+                trees
+                    .entry(id)
+                    .or_insert_with(|| {
+                        let repo = repo.as_ref()?;
+                        repo.find_tree(Oid::from_str(id).ok()?).ok()
+                    })
+                    .clone()
+            });
+            let state = match (&repo, tree) {
+                (Some(repo), Some(tree)) => {
+                    let inside = relative.strip_prefix(&overlay.path).unwrap_or(relative);
+                    match local_lines(repo, &tree, inside, file) {
+                        Some(lines) => VendoredState::Lines(lines),
+                        None => VendoredState::Fallback(overlay.fallback),
+                    }
+                }
+                _ => VendoredState::Fallback(overlay.fallback),
+            };
+            set.files.insert(file.clone(), state);
+        }
+        set
+    }
+}
+
+/// How findings in one vendored file are filtered
+#[derive(Debug, Clone)]
+enum VendoredState {
+    /// Lines changed from upstream; `AllNew` for files added locally
+    Lines(DiffFileLines),
+    /// No upstream to compare with
+    Fallback(GeneratedPolicy),
+}
+
+impl VendoredState {
+    /// Whether no analyzer needs to scan the file
+    fn skipped(&self) -> bool {
+        match self {
+            Self::Lines(DiffFileLines::Lines(lines)) => lines.is_empty(),
+            Self::Lines(DiffFileLines::AllNew) => false,
+            Self::Fallback(policy) => *policy == GeneratedPolicy::Skip,
+        }
+    }
+}
+
+/// The vendored files of one run and the lines patched in each
+#[derive(Debug, Clone, Default)]
+pub struct VendoredSet {
+    files: HashMap<PathBuf, VendoredState>,
+}
+
+impl VendoredSet {
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    fn get(&self, path: &Path, repo_root: &Path) -> Option<&VendoredState> {
+        self.files.get(path).or_else(|| {
+            path.is_relative()
+                .then(|| self.files.get(&repo_root.join(path)))
+                .flatten()
+        })
+    }
+
+    /// Lines of `path` that differ from upstream; `None` when `path` is not
+    /// vendored or has no upstream to compare with
+    pub fn patched_lines(&self, path: &Path, repo_root: &Path) -> Option<&DiffFileLines> {
+        match self.get(path, repo_root)? {
+            VendoredState::Lines(lines) => Some(lines),
+            VendoredState::Fallback(_) => None,
+        }
+    }
+
+    /// Files content analyzers should scan: all of `files`, minus the
+    /// vendored ones that match upstream or fall back to `skip`
+    pub fn analysis_files(&self, files: &[PathBuf]) -> Vec<PathBuf> {
+        if self.files.is_empty() {
+            return files.to_vec();
+        }
+        files
+            .iter()
+            .filter(|f| !self.files.get(*f).is_some_and(VendoredState::skipped))
+            .cloned()
+            .collect()
+    }
+
+    /// Record the vendored files, and the ones no analyzer scans as
+    /// skipped
+    pub fn record(&self, ledger: &mut CoverageLedger) {
+        for (path, state) in &self.files {
+            ledger.vendored(path);
+            if state.skipped() {
+                ledger.dropped(path, SkipReason::VendoredUpstream);
+            }
+        }
+    }
+
+    /// Keep the findings in vendored files only on patched lines, and apply
+    /// the fallback policy where there is no upstream. Returns the findings
+    /// kept and the number dropped.
+    pub fn apply(&self, findings: Vec<Finding>, repo_root: &Path) -> (Vec<Finding>, usize) {
+        if self.files.is_empty() {
+            return (findings, 0);
+        }
+        let mut dropped = 0;
+        let kept = findings
+            .into_iter()
+            .filter_map(|mut f| {
+                if f.is_repository_level() {
+                    return Some(f);
+                }
+                let keep = match self.get(&f.file, repo_root) {
+                    None => true,
+                    Some(VendoredState::Lines(DiffFileLines::AllNew)) => true,
+                    Some(VendoredState::Lines(DiffFileLines::Lines(lines))) => {
+                        lines.contains(&f.line)
+                    }
+                    Some(VendoredState::Fallback(GeneratedPolicy::Skip)) => false,
+                    Some(VendoredState::Fallback(GeneratedPolicy::InfoOnly)) => {
+                        f.severity = Severity::Info;
+                        f.generated = true;
+                        true
+                    }
+                    Some(VendoredState::Fallback(GeneratedPolicy::Analyze)) => true,
+                };
+                if !keep {
+                    dropped += 1;
+                }
+                keep.then_some(f)
+            })
+            .collect();
+        (kept, dropped)
+    }
+}
+
+/// `./third_party/libfoo/` as `third_party/libfoo`
+fn normalize(path: &str) -> PathBuf {
+    Path::new(path)
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect()
+}
+
+/// The first `[[vendored]]` path listed twice or inside another one
+pub fn overlapping(entries: &[VendoredConfig]) -> Option<&str> {
+    let paths: Vec<PathBuf> = entries.iter().map(|e| normalize(&e.path)).collect();
+    // Empty paths are reported on their own
+    let nonempty = |path: &PathBuf| !path.as_os_str().is_empty();
+    entries.iter().enumerate().find_map(|(i, entry)| {
+        let path = &paths[i];
+        paths
+            .iter()
+            .enumerate()
+            .any(|(j, other)| {
+                i != j
+                    && nonempty(path)
+                    && nonempty(other)
+                    && path.starts_with(other)
+                    && (j < i || path != other)
+            })
+            .then_some(entry.path.as_str())
+    })
+}
+
+/// The upstream tree of the directory at `path`: `spec` names a commit,
+/// tag or tree of either the whole repository (the directory is looked up
+/// in it) or the library alone
+fn upstream_tree(repo: &Repository, spec: &str, path: &Path) -> Result<Oid> {
+    let tree = repo
+        .revparse_single(spec)
+        .with_context(|| format!("Failed to resolve git ref '{}'", spec))?
+        .peel_to_tree()
+        .with_context(|| format!("'{}' does not point to a tree", spec))?;
+    match tree.get_path(path) {
+        Ok(entry) if entry.kind() == Some(ObjectType::Tree) => Ok(entry.id()),
+        _ => Ok(tree.id()),
+    }
+}
+
+/// Lines of the working file `file` that differ from `inside` in the
+/// upstream `tree`; `AllNew` when upstream has no such file, `None` when
+/// the file can't be read
+fn local_lines(
+    repo: &Repository,
+    tree: &Tree,
+    inside: &Path,
+    file: &Path,
+) -> Option<DiffFileLines> {
+    let content = std::fs::read(file).ok()?;
+    let Ok(entry) = tree.get_path(inside) else {
+        return Some(DiffFileLines::AllNew);
+    };
+    let Ok(blob) = repo.find_blob(entry.id()) else {
+        return Some(DiffFileLines::AllNew);
+    };
+    let patch = Patch::from_blob_and_buffer(&blob, None, &content, None, None).ok()?;
+    let mut lines = HashSet::new();
+    for hunk in 0..patch.num_hunks() {
+        for i in 0..patch.num_lines_in_hunk(hunk).ok()? {
+            let line = patch.line_in_hunk(hunk, i).ok()?;
+            if line.origin() == '+' {
+                if let Some(number) = line.new_lineno() {
+                    lines.insert(number as usize);
+                }
+            }
+        }
+    }
+    Some(DiffFileLines::Lines(lines))
+}
@@ -0,0 +1,128 @@
+//! Multi-repo workspaces — the repositories `revet multi` reviews together
+//!
+//! A workspace is described by a `revet-workspace.toml` file:
+//!
+//! ```toml
+//! repos = ["services/*", "libs/shared", "../tools"]
+//! exclude = ["services/legacy-*"]
+//! jobs = 4
+//! ```
+//!
+//! Entries in `repos` are paths relative to the workspace file. Entries with
+//! glob characters expand to every matching directory; plain paths are kept
+//! even when missing so the review can report them as failed repos.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Default workspace file name
+pub const WORKSPACE_FILE: &str = "revet-workspace.toml";
+
+/// Contents of a `revet-workspace.toml` file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceConfig {
+    /// Repository paths or directory globs, relative to the workspace file
+    #[serde(default)]
+    pub repos: Vec<String>,
+
+    /// Globs (relative to the workspace file) of repositories to leave out
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// Repositories reviewed concurrently (default: available parallelism)
+    #[serde(default)]
+    pub jobs: Option<usize>,
+}
+
+/// One repository of a resolved workspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceRepo {
+    /// Display name: the path relative to the workspace root
+    pub name: String,
+    /// Absolute path (unchecked — may not exist or not be a git repo)
+    pub path: PathBuf,
+}
+
+impl WorkspaceConfig {
+    /// Load a workspace file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let config: WorkspaceConfig = toml::from_str(&contents)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        if config.jobs == Some(0) {
+            anyhow::bail!("{}: jobs must be at least 1", path.display());
+        }
+        Ok(config)
+    }
+
+    /// Expand `repos` against `root` (the workspace file's directory).
+    ///
+    /// Glob matches are sorted per entry; repos are deduplicated by path in
+    /// the order they are listed, and `exclude` patterns are applied last.
+    pub fn resolve_repos(&self, root: &Path) -> Result<Vec<WorkspaceRepo>> {
+        let exclude = self
+            .exclude
+            .iter()
+            .map(|p| {
+                glob::Pattern::new(p.trim_end_matches('/'))
+                    .with_context(|| format!("invalid exclude pattern {:?}", p))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut seen = HashSet::new();
+        let mut repos = Vec::new();
+        for entry in &self.repos {
+            let entry = entry.trim_end_matches('/');
+            let paths = if is_glob(entry) {
+                let pattern = root.join(entry);
+                let mut matches: Vec<PathBuf> = glob::glob(&pattern.to_string_lossy())
+                    .with_context(|| format!("invalid repos pattern {:?}", entry))?
+                    .filter_map(|p| p.ok())
+                    .filter(|p| p.is_dir())
+                    .collect();
+                matches.sort();
+                matches
+            } else {
+                vec![root.join(entry)]
+            };
+
+            for path in paths {
+                let path = std::fs::canonicalize(&path).unwrap_or(path);
+                let name = repo_name(&path, root, entry);
+                if exclude.iter().any(|p| p.matches(&name)) {
+                    continue;
+                }
+                if seen.insert(path.clone()) {
+                    repos.push(WorkspaceRepo { name, path });
+                }
+            }
+        }
+        Ok(repos)
+    }
+}
+
+fn is_glob(entry: &str) -> bool {
+    entry.contains(['*', '?', '['])
+}
+
+/// Path relative to the workspace root with `/` separators, falling back to
+/// the entry as written for repos outside the root
+fn repo_name(path: &Path, root: &Path, entry: &str) -> String {
+    let root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    match path.strip_prefix(&root) {
+        Ok(rel) if !rel.as_os_str().is_empty() => rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        _ if is_glob(entry) => path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| entry.to_string()),
+        _ => entry.to_string(),
+    }
+}
@@ -0,0 +1,88 @@
+//! Benchmark: `analyze_buffer` latency on a warm graph
+//!
+//! Run with: cargo test --release --test bench_buffer -- --nocapture --ignored
+
+use revet_core::{analyze_buffer, FileGraphCache, GraphContext, RevetConfig, StoragePaths};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+/// Target per-call latency for a 1k-line TypeScript buffer
+const LATENCY_BUDGET: Duration = Duration::from_millis(150);
+
+/// A ~1000-line TypeScript module that imports from `lib/mod{i}.ts`.
+fn typescript_buffer(modules: usize) -> String {
+    let mut src = String::new();
+    for i in 0..modules {
+        src.push_str(&format!("import {{ util{i} }} from './lib/mod{i}';\n"));
+    }
+    let mut i = 0;
+    while src.lines().count() < 1000 {
+        src.push_str(&format!(
+            "\nexport function handler{i}(req: Request, res: Response) {{\n  const value = util{}(req.body);\n  if (value > 10 && value < 100) {{\n    res.send(value);\n  }} else {{\n    res.status(400).send('bad');\n  }}\n}}\n",
+            i % modules
+        ));
+        i += 1;
+    }
+    src
+}
+
+#[test]
+#[ignore] // Run explicitly with --ignored
+fn bench_analyze_buffer_1k_line_typescript() {
+    let dir = TempDir::new().unwrap();
+    let lib = dir.path().join("lib");
+    std::fs::create_dir_all(&lib).unwrap();
+
+    // Warm graph: 200 modules, each exporting a few functions
+    let modules = 200;
+    for i in 0..modules {
+        let path = lib.join(format!("mod{i}.ts"));
+        let mut src = String::new();
+        src.push_str(&format!(
+            "export function util{i}(x: number) {{ return x * {i}; }}\n"
+        ));
+        for j in 0..10 {
+            src.push_str(&format!(
+                "export function extra{i}_{j}(x: number) {{ return util{i}(x) + {j}; }}\n"
+            ));
+        }
+        std::fs::write(&path, src).unwrap();
+    }
+    let buffer = typescript_buffer(modules);
+    let app = dir.path().join("app.ts");
+    std::fs::write(&app, &buffer).unwrap();
+
+    let mut config = RevetConfig::default();
+    config.modules.dead_code = true;
+    config.modules.complexity = true;
+    config.modules.dead_imports = true;
+    let cache = FileGraphCache::at(&StoragePaths::in_repo(dir.path())).enabled(false);
+    let ctx = GraphContext::load_with(dir.path(), &config, &cache).unwrap();
+    println!(
+        "\n=== analyze_buffer ({} lines, {} graph nodes) ===",
+        buffer.lines().count(),
+        ctx.graph().nodes().count()
+    );
+
+    // Warm up
+    analyze_buffer(&app, &buffer, &config, &ctx);
+
+    let runs = 20;
+    let mut worst = Duration::ZERO;
+    let start = Instant::now();
+    for _ in 0..runs {
+        let t = Instant::now();
+        let findings = analyze_buffer(&app, &buffer, &config, &ctx);
+        worst = worst.max(t.elapsed());
+        assert!(!findings.is_empty());
+    }
+    let mean = start.elapsed() / runs;
+    println!("  mean: {:?}  worst: {:?}", mean, worst);
+
+    assert!(
+        mean < LATENCY_BUDGET,
+        "mean latency {:?} exceeds {:?}",
+        mean,
+        LATENCY_BUDGET
+    );
+}
@@ -0,0 +1,167 @@
+//! Benchmark: msgpack vs compact graph cache on a large synthetic graph
+//!
+//! Run with: cargo test --release --test bench_cache -- --nocapture --ignored
+//!
+//! `REVET_BENCH_CACHE_NODES` sets the node count (default 1,000,000). About
+//! 5.5M nodes produce a ~1.4 GB msgpack file, the size of the monorepo cache
+//! that motivated the compact format; at that size the compact load is
+//! expected to finish in under 2 seconds.
+
+use revet_core::cache::compact::CompactGraph;
+use revet_core::graph::{
+    CodeGraph, Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeKind, Parameter,
+};
+use revet_core::{CacheFormat, GraphCache, GraphCacheMeta, NodeId};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use tempfile::TempDir;
+
+/// Nodes in the ~1.4 GB msgpack fixture
+const FULL_SIZE_NODES: usize = 5_500_000;
+/// Compact load budget at `FULL_SIZE_NODES`
+const FULL_SIZE_BUDGET: Duration = Duration::from_secs(2);
+
+/// 20 functions and a class per file, each function calling the next
+fn synthetic_graph(nodes: usize) -> CodeGraph {
+    let root = PathBuf::from("/repo");
+    let mut graph = CodeGraph::new(root.clone());
+    let per_file = 21;
+    for f in 0..nodes.div_ceil(per_file) {
+        let file = root.join(format!("services/svc{}/src/module_{}.py", f % 500, f));
+        let class = graph.add_node(Node::new(
+            NodeKind::Class,
+            format!("Handler{}", f),
+            file.clone(),
+            1,
+            NodeData::Class {
+                base_classes: vec!["BaseHandler".to_string()],
+                methods: vec!["handle".to_string(), "validate".to_string()],
+                fields: vec!["session".to_string()],
+            },
+        ));
+        let mut prev: Option<NodeId> = None;
+        for i in 0..per_file - 1 {
+            let mut node = Node::new(
+                NodeKind::Function,
+                format!("handle_{}_{}", f, i),
+                file.clone(),
+                10 + i * 12,
+                NodeData::Function {
+                    parameters: vec![
+                        Parameter {
+                            name: "request".to_string(),
+                            param_type: Some("Request".to_string()),
+                            default_value: None,
+                        },
+                        Parameter {
+                            name: "timeout".to_string(),
+                            param_type: Some("float".to_string()),
+                            default_value: Some("30.0".to_string()),
+                        },
+                    ],
+                    return_type: Some("Response".to_string()),
+                },
+            );
+            node.set_end_line(20 + i * 12);
+            let id = graph.add_node(node);
+            graph.add_edge(class, id, Edge::new(EdgeKind::Contains));
+            if let Some(prev) = prev {
+                graph.add_edge(
+                    prev,
+                    id,
+                    Edge::with_metadata(
+                        EdgeKind::Calls,
+                        EdgeMetadata::Call {
+                            line: 12 + i * 12,
+                            is_direct: true,
+                        },
+                    ),
+                );
+            }
+            prev = Some(id);
+        }
+    }
+    graph
+}
+
+fn meta() -> GraphCacheMeta {
+    GraphCacheMeta {
+        commit_hash: None,
+        timestamp: SystemTime::now(),
+        file_checksums: HashMap::new(),
+        revet_version: env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+fn file_size(path: &Path) -> f64 {
+    std::fs::metadata(path).unwrap().len() as f64 / (1024.0 * 1024.0)
+}
+
+#[test]
+#[ignore] // Run explicitly with --ignored
+fn bench_graph_cache_formats() {
+    let nodes: usize = std::env::var("REVET_BENCH_CACHE_NODES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1_000_000);
+    let graph = synthetic_graph(nodes);
+    let node_count = graph.nodes().count();
+    println!("\n  Graph cache benchmark — {} nodes", node_count);
+
+    let mut load_times = Vec::new();
+    for format in [CacheFormat::Msgpack, CacheFormat::Compact] {
+        let dir = TempDir::new().unwrap();
+        let cache = GraphCache::with_format(dir.path(), format);
+
+        let start = Instant::now();
+        cache.save(&graph, &meta()).unwrap();
+        let save = start.elapsed();
+
+        let start = Instant::now();
+        let (loaded, _) = cache.load().unwrap().unwrap();
+        let load = start.elapsed();
+        assert_eq!(loaded.nodes().count(), node_count);
+
+        println!(
+            "  {:<8} size {:>8.1} MB   save {:>7.2}s   load {:>7.2}s",
+            format!("{:?}", format),
+            file_size(&cache.graph_path()),
+            save.as_secs_f64(),
+            load.as_secs_f64()
+        );
+
+        if format == CacheFormat::Compact {
+            let start = Instant::now();
+            let view = CompactGraph::open(&cache.graph_path()).unwrap();
+            let found = view
+                .find_nodes(
+                    Path::new("/repo/services/svc7/src/module_7.py"),
+                    "handle_7_3",
+                )
+                .unwrap();
+            println!(
+                "  compact open + indexed lookup (no materialization): {:.3}s",
+                start.elapsed().as_secs_f64()
+            );
+            assert_eq!(found.len(), 1);
+        }
+        load_times.push(load);
+    }
+
+    // Both loads are allocation-bound once every node is materialized; the
+    // compact decode is parallel, so its lead grows with the core count.
+    println!(
+        "  compact speedup: {:.2}x on {} threads",
+        load_times[0].as_secs_f64() / load_times[1].as_secs_f64(),
+        rayon::current_num_threads()
+    );
+    if node_count >= FULL_SIZE_NODES {
+        assert!(
+            load_times[1] < FULL_SIZE_BUDGET,
+            "compact load took {:?} (budget {:?})",
+            load_times[1],
+            FULL_SIZE_BUDGET
+        );
+    }
+}
@@ -35,7 +35,7 @@ fn collect_files_recursive(dir: &std::path::Path, extensions: &[&str], out: &mut
         if path.is_dir() {
             if path
                 .file_name()
-                .map_or(false, |n| n == "target" || n == ".git")
+                .is_some_and(|n| n == "target" || n == ".git")
             {
                 continue;
             }
@@ -0,0 +1,111 @@
+//! Benchmark: storage size of incremental vs full graph store snapshots
+//!
+//! Run with: cargo test --release --test bench_store -- --nocapture --ignored
+//!
+//! Flushes ten snapshots of a synthetic graph that changes 1% of its nodes
+//! between snapshots, once as full snapshots every time and once with the
+//! default delta chain, and compares the SQLite file sizes.
+//! `REVET_BENCH_STORE_NODES` sets the node count (default 20,000).
+
+#![cfg(feature = "cozo-store")]
+
+use revet_core::graph::{CodeGraph, Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeKind};
+use revet_core::store::{reconstruct_graph, CozoStore, GraphStore};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tempfile::TempDir;
+
+const SNAPSHOTS: usize = 10;
+
+/// Functions in files of 50, each calling the next; every 100th function's
+/// return type changes with `version`
+fn synthetic_graph(nodes: usize, version: usize) -> CodeGraph {
+    let mut graph = CodeGraph::new(PathBuf::from("/repo"));
+    let ids: Vec<_> = (0..nodes)
+        .map(|i| {
+            let revision = if i % 100 == 0 { version } else { 0 };
+            graph.add_node(Node::new(
+                NodeKind::Function,
+                format!("handle_{i}"),
+                PathBuf::from(format!("services/svc{}/src/module_{}.py", i % 50, i / 50)),
+                (i % 50) * 12 + 10,
+                NodeData::Function {
+                    parameters: vec![],
+                    return_type: Some(format!("Response{revision}")),
+                },
+            ))
+        })
+        .collect();
+    for i in 0..nodes {
+        graph.add_edge(
+            ids[i],
+            ids[(i + 1) % nodes],
+            Edge::with_metadata(
+                EdgeKind::Calls,
+                EdgeMetadata::Call {
+                    line: (i % 50) * 12 + 11,
+                    is_direct: true,
+                },
+            ),
+        );
+    }
+    graph
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+#[test]
+#[ignore]
+fn bench_snapshot_storage() {
+    let nodes: usize = std::env::var("REVET_BENCH_STORE_NODES")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(20_000);
+    let dir = TempDir::new().unwrap();
+    let full_path = dir.path().join("full.db");
+    let delta_path = dir.path().join("delta.db");
+
+    let (mut full_time, mut delta_time) = (0.0, 0.0);
+    {
+        let full = CozoStore::new_sqlite(&full_path)
+            .unwrap()
+            .with_full_every(1);
+        let deltas = CozoStore::new_sqlite(&delta_path).unwrap();
+        for v in 0..SNAPSHOTS {
+            let graph = synthetic_graph(nodes, v);
+            let name = format!("s{v}");
+            let start = Instant::now();
+            full.flush(&graph, &name).unwrap();
+            full_time += start.elapsed().as_secs_f64();
+            let start = Instant::now();
+            deltas.flush(&graph, &name).unwrap();
+            delta_time += start.elapsed().as_secs_f64();
+        }
+
+        let last = format!("s{}", SNAPSHOTS - 1);
+        let root = Path::new("/repo");
+        let from_full = reconstruct_graph(&full, &last, root).unwrap();
+        let from_deltas = reconstruct_graph(&deltas, &last, root).unwrap();
+        assert_eq!(
+            serde_json::to_vec(&from_full.nodes().map(|(_, n)| n).collect::<Vec<_>>()).unwrap(),
+            serde_json::to_vec(&from_deltas.nodes().map(|(_, n)| n).collect::<Vec<_>>()).unwrap()
+        );
+    }
+
+    let (full, delta) = (file_size(&full_path), file_size(&delta_path));
+    println!("{SNAPSHOTS} snapshots of {nodes} nodes, 1% changed between snapshots");
+    println!(
+        "  full every time: {:>8} KiB  flush {:.2}s",
+        full / 1024,
+        full_time
+    );
+    println!(
+        "  delta chain:     {:>8} KiB  flush {:.2}s  ({:.0}% of full)",
+        delta / 1024,
+        delta_time,
+        delta as f64 * 100.0 / full as f64
+    );
+    assert!(delta * 4 < full, "delta {delta} bytes vs full {full} bytes");
+}
@@ -0,0 +1,127 @@
+//! Fixture helpers shared by the analyzer integration tests
+
+#![allow(dead_code)]
+
+use git2::{IndexAddOption, Repository, Signature, Time};
+use revet_core::analyzer::AnalyzerDispatcher;
+use revet_core::config::RevetConfig;
+use revet_core::discovery::discover_files_extended;
+use revet_core::finding::Finding;
+use revet_core::{MutationJournal, StoragePaths};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// `tests/fixtures/<name>` at the workspace root
+pub fn fixture_root(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures")
+        .join(name)
+        .canonicalize()
+        .unwrap()
+}
+
+/// The fixture's own `.revet.toml`
+pub fn fixture_config(name: &str) -> RevetConfig {
+    RevetConfig::from_file(&fixture_root(name).join(".revet.toml")).unwrap()
+}
+
+/// Files under `root` with one of `extensions`
+pub fn discover(root: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    discover_files_extended(root, extensions, &[], &[]).unwrap()
+}
+
+/// Every enabled analyzer over `root`, the way `revet` discovers files:
+/// analyzers' extra extensions added, `[ignore] paths` left out
+pub fn run(root: &Path, extensions: &[&str], config: &RevetConfig) -> Vec<Finding> {
+    let dispatcher = AnalyzerDispatcher::new_with_config(config);
+    let mut extensions = extensions.to_vec();
+    extensions.extend(dispatcher.extra_extensions(config));
+    let files = discover_files_extended(root, &extensions, &[], &config.ignore.paths).unwrap();
+    dispatcher.run_all(&files, root, config)
+}
+
+/// Findings for the fixture under its own `.revet.toml`
+pub fn fixture_findings(name: &str, extensions: &[&str]) -> (PathBuf, Vec<Finding>) {
+    let root = fixture_root(name);
+    let findings = run(&root, extensions, &fixture_config(name));
+    (root, findings)
+}
+
+/// A journal for fixes in tests, logging to `log_dir`
+pub fn journal(log_dir: &Path) -> MutationJournal {
+    MutationJournal::new(
+        &StoragePaths::in_repo(log_dir),
+        log_dir,
+        vec!["revet".to_string(), "--fix".to_string()],
+    )
+}
+
+/// Run git in `dir` with a fixed committer, asserting it succeeds; its
+/// trimmed stdout
+pub fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=dev", "-c", "user.email=dev@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?}", args);
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+/// Commit time of day 0 in scripted git histories
+pub const T0: i64 = 1_700_000_000;
+/// One day in seconds
+pub const DAY: i64 = 24 * 3600;
+
+/// A Python function with `branches` `if` statements (complexity
+/// `branches + 1`), spanning lines 1..=branches+2
+pub fn python_fn(name: &str, branches: usize) -> String {
+    let mut src = format!("def {}(x):\n", name);
+    for i in 0..branches {
+        src.push_str(&format!("    if x == {}: return {}\n", i, i));
+    }
+    src.push_str("    return -1\n");
+    src
+}
+
+/// Write (`Some`) or delete (`None`) files, then commit everything as
+/// `author` (name, email) on `day`
+pub fn commit(repo: &Repository, author: (&str, &str), files: &[(&str, Option<String>)], day: i64) {
+    let root = repo.workdir().unwrap().to_path_buf();
+    for (path, content) in files {
+        let path = root.join(path);
+        match content {
+            Some(content) => {
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, content).unwrap();
+            }
+            None => fs::remove_file(path).unwrap(),
+        }
+    }
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"].iter(), IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.update_all(["*"].iter(), None).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = Signature::new(author.0, author.1, &Time::new(T0 + day * DAY, 0)).unwrap();
+    let parents: Vec<git2::Commit> = repo
+        .head()
+        .ok()
+        .and_then(|h| h.peel_to_commit().ok())
+        .into_iter()
+        .collect();
+    let parents: Vec<&git2::Commit> = parents.iter().collect();
+    repo.commit(
+        Some("HEAD"),
+        &sig,
+        &sig,
+        &format!("day {}", day),
+        &tree,
+        &parents,
+    )
+    .unwrap();
+}
@@ -0,0 +1,415 @@
+//! Integration tests for A11yAnalyzer
+
+mod common;
+
+use revet_core::analyzer::a11y::A11yAnalyzer;
+use revet_core::analyzer::{Analyzer, AnalyzerDispatcher};
+use revet_core::config::RevetConfig;
+use revet_core::finding::{Confidence, Finding, Severity};
+use std::path::Path;
+
+const FIXTURE: &str = "a11y";
+const EXTENSIONS: &[&str] = &[".js", ".jsx", ".ts", ".tsx"];
+
+/// (repo-relative file, line, sub-rule) for each A11Y finding, sorted
+fn summary(findings: &[Finding], root: &Path) -> Vec<(String, usize, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("A11Y"))
+        .map(|f| {
+            let file = f.file.strip_prefix(root).unwrap_or(&f.file);
+            (
+                file.to_string_lossy().replace('\\', "/"),
+                f.line,
+                f.rule.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+fn row(file: &str, line: usize, rule: &str) -> (String, usize, String) {
+    (file.to_string(), line, format!("a11y:{}", rule))
+}
+
+fn analyze(file: &str, content: &str) -> Vec<Finding> {
+    A11yAnalyzer::new().analyze_source(Path::new(file), content, Path::new("."))
+}
+
+/// (line, sub-rule) pairs
+fn lines(findings: &[Finding]) -> Vec<(usize, String)> {
+    findings
+        .iter()
+        .map(|f| (f.line, f.rule.clone().unwrap_or_default()))
+        .collect()
+}
+
+fn at(line: usize, rule: &str) -> (usize, String) {
+    (line, rule.to_string())
+}
+
+// ── Fixture ──────────────────────────────────────────────────────
+
+#[test]
+fn test_fixture() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    assert_eq!(
+        summary(&findings, &root),
+        vec![
+            row("public/checkout.html", 2, "html_lang"),
+            row("public/checkout.html", 12, "img_alt"),
+            row("public/checkout.html", 13, "click_events"),
+            row("public/checkout.html", 14, "click_events"),
+            row("public/checkout.html", 15, "click_events"),
+            row("public/checkout.html", 16, "label"),
+            row("public/checkout.html", 17, "label"),
+            row("public/checkout.html", 18, "tabindex"),
+            row("templates/SignupForm.vue", 4, "img_alt"),
+            row("templates/SignupForm.vue", 6, "click_events"),
+            row("templates/SignupForm.vue", 9, "label"),
+            row("templates/SignupForm.vue", 10, "tabindex"),
+            row("web/Checkout.jsx", 6, "img_alt"),
+            row("web/Checkout.jsx", 7, "click_events"),
+            row("web/Checkout.jsx", 10, "label"),
+            row("web/Checkout.jsx", 11, "label"),
+            row("web/Checkout.jsx", 14, "tabindex"),
+            row("web/RootLayout.tsx", 3, "html_lang"),
+        ]
+    );
+}
+
+#[test]
+fn test_fixture_messages_severities_and_confidence() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let at = |file: &str, line: usize| {
+        findings
+            .iter()
+            .find(|f| f.file == root.join(file) && f.line == line)
+            .unwrap_or_else(|| panic!("no finding at {}:{}", file, line))
+    };
+
+    let alt = at("web/Checkout.jsx", 6);
+    assert_eq!(alt.message, "`<img>` has no alt text");
+    assert_eq!(alt.severity, Severity::Warning);
+    assert_eq!(alt.confidence, Confidence::High);
+    assert_eq!((alt.column, alt.end_column), (Some(7), Some(11)));
+    assert!(alt.suggestion.as_ref().unwrap().contains("alt=\"\""));
+
+    let click = at("web/Checkout.jsx", 7);
+    assert_eq!(
+        click.message,
+        "`<div>` handles clicks but has no keyboard handler or role"
+    );
+    assert_eq!(click.confidence, Confidence::Medium);
+    assert!(click
+        .suggestion
+        .as_ref()
+        .unwrap()
+        .contains("role=\"button\" tabIndex={0} onKeyDown={…}"));
+    let html_click = at("public/checkout.html", 13);
+    assert!(html_click
+        .suggestion
+        .as_ref()
+        .unwrap()
+        .contains("role=\"button\" tabindex=\"0\""));
+
+    // The suggested label names the field's id when it has one
+    let label = at("web/Checkout.jsx", 11);
+    assert_eq!(
+        label.message,
+        "Form field `<select>` has no associated label"
+    );
+    assert_eq!(label.confidence, Confidence::Medium);
+    assert!(label
+        .suggestion
+        .as_ref()
+        .unwrap()
+        .contains("<label htmlFor=\"country\">"));
+    assert!(at("public/checkout.html", 16)
+        .suggestion
+        .as_ref()
+        .unwrap()
+        .contains("<label for=\"…\">"));
+
+    let tabindex = at("web/Checkout.jsx", 14);
+    assert_eq!(
+        tabindex.message,
+        "`tabIndex={2}` on `<button>` moves it ahead of the page's natural tab order"
+    );
+    assert!(tabindex
+        .suggestion
+        .as_ref()
+        .unwrap()
+        .contains("`tabIndex={0}`"));
+    assert!(at("public/checkout.html", 18)
+        .suggestion
+        .as_ref()
+        .unwrap()
+        .contains("`tabindex=\"0\"`"));
+
+    let lang = at("public/checkout.html", 2);
+    assert_eq!(lang.message, "`<html>` has no lang attribute");
+    assert_eq!((lang.column, lang.end_column), (Some(1), Some(6)));
+
+    // `<IMG>` is HTML, not a component
+    assert_eq!(
+        at("public/checkout.html", 12).message,
+        "`<img>` has no alt text"
+    );
+}
+
+#[test]
+fn test_sub_rules_can_be_disabled() {
+    let mut config = common::fixture_config(FIXTURE);
+    config.analyzers.rules.insert(
+        "a11y".to_string(),
+        toml::from_str("disable = [\"click_events\", \"label\"]").unwrap(),
+    );
+    let root = common::fixture_root(FIXTURE);
+    let findings = common::run(&root, EXTENSIONS, &config);
+    let rules: Vec<String> = summary(&findings, &root)
+        .into_iter()
+        .map(|(_, _, rule)| rule)
+        .collect();
+    assert_eq!(rules.len(), 8);
+    assert!(rules
+        .iter()
+        .all(|r| !r.ends_with("click_events") && !r.ends_with("label")));
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let config = RevetConfig::default();
+    assert!(!A11yAnalyzer::new().is_enabled(&config));
+    assert!(AnalyzerDispatcher::new_with_config(&config)
+        .extra_extensions(&config)
+        .iter()
+        .all(|e| *e != ".vue"));
+}
+
+// ── JSX ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_spread_may_supply_missing_attributes() {
+    let findings = analyze(
+        "Card.tsx",
+        r#"const Card = (props) => (
+  <div {...props.root} onClick={props.onOpen}>
+    <img {...props.image} />
+    <input {...register("email")} />
+    <span {...rest} tabIndex={4} />
+  </div>
+);
+"#,
+    );
+    assert_eq!(lines(&findings), vec![at(5, "tabindex")]);
+}
+
+#[test]
+fn test_components_are_not_checked() {
+    let findings = analyze(
+        "Page.jsx",
+        r#"export const Page = () => (
+  <Layout>
+    <Image src="/hero.png" />
+    <Card onClick={open} />
+    <Input type="email" />
+    <Foo.Bar onClick={open} />
+  </Layout>
+);
+"#,
+    );
+    assert!(findings.is_empty(), "{:?}", lines(&findings));
+}
+
+#[test]
+fn test_label_association_in_jsx() {
+    let findings = analyze(
+        "Form.jsx",
+        r#"export const Form = ({ field }) => (
+  <form>
+    <label>
+      Name
+      <span>
+        <input type="text" />
+      </span>
+    </label>
+    <Field.Label htmlFor="age">Age</Field.Label>
+    <input id="age" type="number" />
+    <input id={field.id} />
+    <input id="zip" />
+    <input aria-labelledby="zip-label" />
+    <input title="Code" />
+    <input type={field.type} />
+    <label icon={<input />}>Icon</label>
+  </form>
+);
+"#,
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![at(12, "label"), at(16, "label")],
+        "{:?}",
+        findings
+    );
+}
+
+#[test]
+fn test_computed_label_target_covers_every_id() {
+    let findings = analyze(
+        "Form.jsx",
+        r#"export const Form = ({ name }) => (
+  <>
+    <label htmlFor={name}>Name</label>
+    <input id="first" />
+    <input />
+  </>
+);
+"#,
+    );
+    assert_eq!(lines(&findings), vec![at(5, "label")]);
+}
+
+#[test]
+fn test_click_events_in_jsx() {
+    let findings = analyze(
+        "Menu.jsx",
+        r#"export const Menu = ({ go }) => (
+  <ul>
+    <li onClick={go}>Home</li>
+    <li onClick={go} onKeyUp={go}>About</li>
+    <li onClick={go} role="menuitem">Blog</li>
+    <a onClick={go}>Contact</a>
+    <a href="/jobs" onClick={go}>Jobs</a>
+    <button onClick={go}>Go</button>
+    <my-widget onClick={go} />
+  </ul>
+);
+"#,
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![at(3, "click_events"), at(6, "click_events")]
+    );
+}
+
+#[test]
+fn test_hidden_images_and_tabindex_values() {
+    let findings = analyze(
+        "Icons.jsx",
+        r#"export const Icons = () => (
+  <>
+    <img src="a.svg" aria-hidden />
+    <img src="b.svg" aria-hidden="false" />
+    <img src="c.svg" role="none" />
+    <div tabIndex="1" />
+    <div tabIndex={"5"} />
+    <div tabIndex={0} />
+    <div tabIndex={order} />
+  </>
+);
+"#,
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![at(4, "img_alt"), at(6, "tabindex"), at(7, "tabindex")]
+    );
+}
+
+#[test]
+fn test_ts_files_are_not_scanned() {
+    assert!(!A11yAnalyzer::new().accepts(Path::new("src/util.ts")));
+    assert!(A11yAnalyzer::new().accepts(Path::new("src/App.tsx")));
+    assert!(A11yAnalyzer::new().accepts(Path::new("views/index.html.erb")));
+}
+
+// ── HTML templates ───────────────────────────────────────────────
+
+#[test]
+fn test_html_tokenizer_skips_comments_and_raw_text() {
+    let findings = analyze(
+        "index.html",
+        r#"<html lang="en">
+<style>
+  a > img { border: 0 }
+</style>
+<!--
+  <img src="old.png">
+-->
+<script type="module">
+  if (a < b) document.body.innerHTML = '<img src="x.png">';
+</script>
+<p>1 < 2 and <b>bold</b></p>
+<img
+  src="multi-line.png"
+  class="hero"
+>
+</html>
+"#,
+    );
+    assert_eq!(lines(&findings), vec![at(12, "img_alt")]);
+}
+
+#[test]
+fn test_framework_bindings_in_templates() {
+    let findings = analyze(
+        "Widget.svelte",
+        r#"<script>
+  export let alt;
+</script>
+
+<img src={url} {alt} />
+<img src={url} alt={caption} />
+<img src={url} {...$$restProps} />
+<img src={url} />
+<div on:click|preventDefault={toggle}>Toggle</div>
+<span bind:tabindex={order} />
+"#,
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![at(8, "img_alt"), at(9, "click_events")]
+    );
+
+    let angular = analyze(
+        "card.component.html",
+        r#"<img [src]="url" [attr.alt]="caption">
+<div (click)="open()">Open</div>
+<div (click)="open()" (keydown.enter)="open()">Open</div>
+<input [(ngModel)]="name">
+"#,
+    );
+    assert_eq!(lines(&angular), vec![at(2, "click_events"), at(4, "label")]);
+}
+
+#[test]
+fn test_template_interpolation_counts_as_spread() {
+    let findings = analyze(
+        "show.html.erb",
+        r#"<html <%= lang_attributes %>>
+<img src="<%= @user.avatar %>" <%= tag_options %>>
+<img src="<%= @user.avatar %>">
+<label>Email <%= f.email_field :email %></label>
+</html>
+"#,
+    );
+    assert_eq!(lines(&findings), vec![at(3, "img_alt")]);
+}
+
+#[test]
+fn test_label_wrapping_in_html() {
+    let findings = analyze(
+        "form.html",
+        r#"<html lang="en">
+<LABEL>Name <INPUT type="text"></LABEL>
+<label for="city">City</label>
+<select id="city"></select>
+<select id="state"></select>
+<input type="checkbox">
+<input type=RESET>
+</html>
+"#,
+    );
+    assert_eq!(lines(&findings), vec![at(5, "label"), at(6, "label")]);
+}
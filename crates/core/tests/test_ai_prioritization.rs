@@ -0,0 +1,251 @@
+//! Tests for ranking findings for AI enrichment and fitting them to the budget
+
+use revet_core::ai::{self, plan, prioritize, PriorityContext};
+use revet_core::config::{AIConfig, AiPrioritizationConfig};
+use revet_core::graph::{CodeGraph, Edge, EdgeKind, Node, NodeData, NodeKind};
+use revet_core::{Baseline, Confidence, Finding, RevetConfig, Severity};
+use std::path::{Path, PathBuf};
+
+const ROOT: &str = "/repo";
+
+fn finding(id: &str, severity: Severity, file: &str, line: usize) -> Finding {
+    Finding {
+        id: id.to_string(),
+        severity,
+        message: format!("{} message", id),
+        file: PathBuf::from(ROOT).join(file),
+        line,
+        ..Default::default()
+    }
+}
+
+fn ids(findings: &[Finding], indices: impl IntoIterator<Item = usize>) -> Vec<String> {
+    indices
+        .into_iter()
+        .map(|i| findings[i].id.clone())
+        .collect()
+}
+
+fn ranked(findings: &[Finding], context: &PriorityContext) -> Vec<String> {
+    let weights = AiPrioritizationConfig::default();
+    let scored = prioritize(findings, Path::new(ROOT), &weights, context);
+    ids(findings, scored.iter().map(|s| s.index))
+}
+
+/// `lookup` (lines 1–10 of `src/orders.py`) has 12 callers; `helper` has none
+fn graph() -> CodeGraph {
+    let mut graph = CodeGraph::new(PathBuf::from(ROOT));
+    let function = |graph: &mut CodeGraph, name: &str, file: &str, line: usize, end: usize| {
+        let mut node = Node::new(
+            NodeKind::Function,
+            name.to_string(),
+            PathBuf::from(ROOT).join(file),
+            line,
+            NodeData::Function {
+                parameters: vec![],
+                return_type: None,
+            },
+        );
+        node.set_end_line(end);
+        graph.add_node(node)
+    };
+    let lookup = function(&mut graph, "lookup", "src/orders.py", 1, 10);
+    function(&mut graph, "helper", "src/orders.py", 12, 20);
+    for i in 0..12 {
+        let caller = function(&mut graph, &format!("caller_{}", i), "src/app.py", i, i);
+        graph.add_edge(caller, lookup, Edge::new(EdgeKind::Calls));
+    }
+    graph
+}
+
+#[test]
+fn test_score_formula_pinned() {
+    let weights = AiPrioritizationConfig::default();
+    let error = finding("SQL-001", Severity::Error, "a.py", 1);
+    assert_eq!(ai::score(&error, false, 0, false, &weights), 60.0);
+    assert_eq!(ai::score(&error, true, 255, true, &weights), 100.0);
+
+    let mut warning = finding("ERR-001", Severity::Warning, "a.py", 1);
+    warning.confidence = Confidence::Medium;
+    // 20 + 10 + log2(16) / 8 × 10
+    assert_eq!(ai::score(&warning, false, 15, false, &weights), 35.0);
+    warning.confidence = Confidence::Low;
+    assert_eq!(ai::score(&warning, false, 0, true, &weights), 30.0);
+}
+
+#[test]
+fn test_ranking_uses_every_signal() {
+    let findings = vec![
+        finding("ERR-001", Severity::Warning, "tests/helpers.py", 3),
+        finding("SQL-001", Severity::Error, "src/payments/charge.py", 8),
+        finding("SEC-001", Severity::Error, "src/config.py", 2),
+        finding("ERR-002", Severity::Warning, "src/orders.py", 4),
+        finding("ERR-003", Severity::Warning, "src/orders.py", 14),
+    ];
+    let critical = vec!["src/payments/**".to_string()];
+    let graph = graph();
+    let context = PriorityContext {
+        graph: Some(&graph),
+        baseline: None,
+        critical_paths: &critical,
+    };
+    // ERR-002 sits in `lookup` with 12 callers; ERR-001 and ERR-003 tie and
+    // fall back to file order
+    assert_eq!(
+        ranked(&findings, &context),
+        ["SQL-001", "SEC-001", "ERR-002", "ERR-003", "ERR-001"]
+    );
+
+    // Without a graph the dependents part comes from the finding itself
+    let mut findings = findings;
+    findings[4].affected_dependents = 40;
+    let context = PriorityContext {
+        critical_paths: &critical,
+        ..Default::default()
+    };
+    assert_eq!(
+        ranked(&findings, &context),
+        ["SQL-001", "SEC-001", "ERR-003", "ERR-002", "ERR-001"]
+    );
+}
+
+#[test]
+fn test_baseline_and_filters() {
+    let old = finding("SEC-001", Severity::Error, "src/config.py", 2);
+    let baseline = Baseline::from_findings(std::slice::from_ref(&old), Path::new(ROOT), None);
+    let mut with_suggestion = finding("ERR-009", Severity::Error, "src/a.py", 1);
+    with_suggestion.suggestion = Some("use ?".to_string());
+    let findings = vec![
+        old,
+        finding("SEC-002", Severity::Error, "src/keys.py", 5),
+        finding("ERR-001", Severity::Warning, "src/orders.py", 4),
+        finding("DBG-001", Severity::Info, "src/orders.py", 9),
+        with_suggestion,
+    ];
+    let context = PriorityContext {
+        baseline: Some(&baseline),
+        ..Default::default()
+    };
+    // The new error outranks the baselined one; info findings and findings
+    // with suggestions are never eligible
+    assert_eq!(
+        ranked(&findings, &context),
+        ["SEC-002", "SEC-001", "ERR-001"]
+    );
+
+    let only_new = AiPrioritizationConfig {
+        only_new: true,
+        ..Default::default()
+    };
+    let scored = prioritize(&findings, Path::new(ROOT), &only_new, &context);
+    assert_eq!(
+        ids(&findings, scored.iter().map(|s| s.index)),
+        ["SEC-002", "ERR-001"]
+    );
+
+    let errors = AiPrioritizationConfig {
+        min_severity: "error".to_string(),
+        ..only_new
+    };
+    let scored = prioritize(&findings, Path::new(ROOT), &errors, &context);
+    assert_eq!(ids(&findings, scored.iter().map(|s| s.index)), ["SEC-002"]);
+}
+
+#[test]
+fn test_ranking_is_independent_of_input_order() {
+    let findings: Vec<Finding> = (0..8)
+        .map(|i| {
+            let severity = if i % 3 == 0 {
+                Severity::Error
+            } else {
+                Severity::Warning
+            };
+            finding(&format!("ERR-{:03}", i), severity, "src/a.py", 10 - i)
+        })
+        .collect();
+    let mut reversed = findings.clone();
+    reversed.reverse();
+    let context = PriorityContext::default();
+    assert_eq!(ranked(&findings, &context), ranked(&reversed, &context));
+}
+
+#[test]
+fn test_plan_fills_budget_by_priority() {
+    let findings: Vec<Finding> = vec![
+        finding("ERR-001", Severity::Warning, "src/a.py", 1),
+        finding("SQL-001", Severity::Error, "src/a.py", 2),
+        finding("ERR-002", Severity::Warning, "src/a.py", 3),
+        finding("SEC-001", Severity::Error, "src/a.py", 4),
+    ];
+    let config = AIConfig::default();
+    let context = PriorityContext::default();
+    let root = Path::new(ROOT);
+
+    let all = plan(&findings, root, &config, 100.0, &context).unwrap();
+    assert!(all.skipped.is_empty());
+    assert_eq!(all.score_cutoff, None);
+    assert_eq!(ids(&findings, all.batches[0].findings.clone()).len(), 4);
+
+    // Exactly what the two errors cost on their own
+    let errors: Vec<Finding> = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Error)
+        .cloned()
+        .collect();
+    let budget = ai::AiEstimate::new(
+        &plan(&errors, root, &config, 100.0, &context).unwrap(),
+        &config,
+        100.0,
+    )
+    .estimated_cost;
+
+    let capped = plan(&findings, root, &config, budget, &context).unwrap();
+    assert_eq!(
+        ids(&findings, capped.batches[0].findings.clone()),
+        ["SQL-001", "SEC-001"]
+    );
+    assert_eq!(
+        ids(&findings, capped.skipped.clone()),
+        ["ERR-001", "ERR-002"]
+    );
+    assert_eq!(capped.score_cutoff, Some(70.0));
+
+    let estimate = ai::AiEstimate::new(&capped, &config, budget);
+    assert_eq!(estimate.eligible, 4);
+    assert_eq!(estimate.skipped_budget, 2);
+    assert!(estimate.exceeds_max_cost);
+    assert!(estimate.estimated_cost <= budget);
+
+    let none = plan(&findings, root, &config, 0.0, &context).unwrap();
+    assert!(none.batches.is_empty());
+    assert_eq!(none.skipped.len(), 4);
+    assert_eq!(none.score_cutoff, None);
+}
+
+#[test]
+fn test_prioritization_config() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[ai.prioritization]
+severity = 100
+new = -5
+min_severity = "info"
+"#,
+    )
+    .unwrap();
+    let weights = &config.ai.prioritization;
+    assert_eq!(weights.severity, 100.0);
+    assert_eq!(weights.confidence, 20.0);
+    assert!(!weights.only_new);
+
+    let (errors, _) = config.validate();
+    let errors: Vec<&String> = errors
+        .iter()
+        .filter(|e| e.contains("[ai.prioritization]"))
+        .collect();
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(errors.iter().any(|e| e.contains("new = -5 is invalid")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("min_severity = \"info\" is invalid")));
+}
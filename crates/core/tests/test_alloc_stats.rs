@@ -0,0 +1,34 @@
+//! Integration tests for the counting allocator (`--features alloc-stats`)
+
+#![cfg(feature = "alloc-stats")]
+
+use revet_core::alloc_stats::{measure, CountingAllocator};
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_measure_reports_the_peak_of_the_closure() {
+    let (len, peak) = measure(|| {
+        let big = vec![0u8; 1 << 20];
+        let len = big.len();
+        drop(big);
+        let small = vec![0u8; 1 << 10];
+        len + small.len()
+    });
+    assert_eq!(len, (1 << 20) + (1 << 10));
+    assert!(peak >= 1 << 20, "{}", peak);
+    assert!(peak < 2 << 20, "{}", peak);
+}
+
+#[test]
+fn test_nested_measure_keeps_the_outer_peak() {
+    let (inner, outer) = measure(|| {
+        let big = vec![0u8; 1 << 20];
+        let (_, inner) = measure(|| vec![0u8; 1 << 10].len());
+        drop(big);
+        inner
+    });
+    assert!(inner < 1 << 20, "{}", inner);
+    assert!(outer >= 1 << 20, "{}", outer);
+}
@@ -1,4 +1,10 @@
-use revet_core::{filter_findings, Baseline, BaselineEntry, Finding, Severity};
+mod common;
+
+use revet_core::analyzer::registry::Source;
+use revet_core::baseline::{self, is_date};
+use revet_core::{
+    entry_statuses, filter_findings, Baseline, BaselineEntry, EntryStatus, Finding, Severity,
+};
 use std::path::PathBuf;
 use tempfile::TempDir;
 
@@ -28,10 +34,10 @@ fn test_save_load_roundtrip() {
 
     let baseline = Baseline::from_findings(&findings, root, Some("abc123".to_string()));
     assert_eq!(baseline.count, 2);
-    assert_eq!(baseline.version, "1");
+    assert_eq!(baseline.version, "2");
     assert_eq!(baseline.commit, Some("abc123".to_string()));
 
-    baseline.save(root).unwrap();
+    baseline.save(root, &mut common::journal(root)).unwrap();
 
     let loaded = Baseline::load(root)
         .unwrap()
@@ -59,6 +65,11 @@ fn test_filter_findings() {
         entries: vec![BaselineEntry {
             file: "src/main.py".to_string(),
             message: "Hardcoded AWS access key detected".to_string(),
+            rule: None,
+            source: None,
+            generated: false,
+            expires: None,
+            line: None,
         }],
     };
 
@@ -107,12 +118,12 @@ fn test_clear_baseline() {
     let root = tmp.path();
 
     // Clear when no baseline exists → false
-    assert!(!Baseline::clear(root).unwrap());
+    assert!(!Baseline::clear(root, &mut common::journal(root)).unwrap());
 
     // Save a baseline, then clear → true
     let baseline = Baseline::from_findings(&[], root, None);
-    baseline.save(root).unwrap();
-    assert!(Baseline::clear(root).unwrap());
+    baseline.save(root, &mut common::journal(root)).unwrap();
+    assert!(Baseline::clear(root, &mut common::journal(root)).unwrap());
 
     // Verify it's gone
     assert!(Baseline::load(root).unwrap().is_none());
@@ -131,6 +142,11 @@ fn test_fingerprint_ignores_line_number() {
         entries: vec![BaselineEntry {
             file: "src/main.py".to_string(),
             message: "Hardcoded AWS access key detected".to_string(),
+            rule: None,
+            source: None,
+            generated: false,
+            expires: None,
+            line: None,
         }],
     };
 
@@ -159,6 +175,11 @@ fn test_different_message_not_matched() {
         entries: vec![BaselineEntry {
             file: "src/main.py".to_string(),
             message: "Hardcoded AWS access key detected".to_string(),
+            rule: None,
+            source: None,
+            generated: false,
+            expires: None,
+            line: None,
         }],
     };
 
@@ -179,3 +200,180 @@ fn test_load_nonexistent() {
     let tmp = TempDir::new().unwrap();
     assert!(Baseline::load(tmp.path()).unwrap().is_none());
 }
+
+#[test]
+fn test_entries_record_rule_and_source() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path();
+
+    let mut secret = make_finding("src/main.py", "Hardcoded AWS access key detected", 10);
+    secret.id = "SEC-001".to_string();
+    let mut acme = make_finding("src/main.py", "Acme key", 11);
+    acme.id = "SEC-002".to_string();
+    acme.rule = Some("security:custom-acme".to_string());
+    let mut todo = make_finding("src/main.py", "TODO left in code", 12);
+    todo.id = "ACME-001".to_string();
+
+    let baseline = Baseline::from_findings(&[secret, acme, todo], root, None);
+    let recorded: Vec<_> = baseline
+        .entries
+        .iter()
+        .map(|e| (e.rule.as_deref().unwrap(), e.source.clone().unwrap()))
+        .collect();
+    assert_eq!(
+        recorded,
+        vec![
+            ("SEC", Source::Builtin),
+            ("security:custom-acme", Source::Custom),
+            ("ACME", Source::Custom),
+        ]
+    );
+
+    let json = serde_json::to_string(&baseline.entries[1]).unwrap();
+    assert!(json.contains(r#""source":"custom""#), "{json}");
+}
+
+#[test]
+fn test_source_must_match() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path();
+
+    let mut plugin = make_finding("src/main.py", "Weak hash", 10);
+    plugin.id = "PLUGIN-crypto-001".to_string();
+    let baseline = Baseline::from_findings(&[plugin], root, None);
+    assert_eq!(
+        baseline.entries[0].source,
+        Some(Source::Plugin("crypto".to_string()))
+    );
+
+    // Same file and message from a renamed plugin → not suppressed
+    let mut renamed = make_finding(&root.join("src/main.py").to_string_lossy(), "Weak hash", 10);
+    renamed.id = "PLUGIN-hashes-001".to_string();
+    let (new, suppressed) = filter_findings(vec![renamed], &baseline, root);
+    assert_eq!(suppressed.len(), 0);
+    assert_eq!(new.len(), 1);
+
+    let mut same = make_finding(&root.join("src/main.py").to_string_lossy(), "Weak hash", 3);
+    same.id = "PLUGIN-crypto-004".to_string();
+    let (new, suppressed) = filter_findings(vec![same], &baseline, root);
+    assert_eq!(suppressed.len(), 1);
+    assert_eq!(new.len(), 0);
+}
+
+#[test]
+fn test_legacy_baseline_loads() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path();
+    std::fs::create_dir_all(root.join(".revet-cache")).unwrap();
+    std::fs::write(
+        root.join(".revet-cache/baseline.json"),
+        r#"{"version": "1", "created_at": "0", "commit": null, "count": 1,
+            "entries": [{"file": "src/main.py", "message": "Weak hash"}]}"#,
+    )
+    .unwrap();
+
+    let baseline = Baseline::load(root).unwrap().unwrap();
+    assert_eq!(baseline.entries[0].source, None);
+
+    // Legacy entries match on file and message from any source
+    let mut finding = make_finding(&root.join("src/main.py").to_string_lossy(), "Weak hash", 3);
+    finding.id = "PLUGIN-crypto-001".to_string();
+    let (_, suppressed) = filter_findings(vec![finding], &baseline, root);
+    assert_eq!(suppressed.len(), 1);
+}
+
+#[test]
+fn test_expired_entries_stop_matching() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path();
+    let file = root.join("src/main.py").to_string_lossy().to_string();
+    let finding = make_finding(&file, "Weak hash", 3);
+
+    let mut baseline = Baseline::from_findings(std::slice::from_ref(&finding), root, None);
+    baseline.entries[0].expires = Some("2099-01-31".to_string());
+    let (new, _) = filter_findings(vec![finding.clone()], &baseline, root);
+    assert!(new.is_empty());
+
+    // Expired on or before today: the finding is reported again
+    baseline.entries[0].expires = Some(baseline::today());
+    assert!(baseline.entries[0].is_expired(&baseline::today()));
+    let (new, suppressed) = filter_findings(vec![finding], &baseline, root);
+    assert_eq!(new.len(), 1);
+    assert!(suppressed.is_empty());
+}
+
+#[test]
+fn test_expiry_survives_save_and_rebaseline() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path();
+    let file = root.join("src/main.py").to_string_lossy().to_string();
+    let findings = vec![
+        make_finding(&file, "Weak hash", 3),
+        make_finding(&file, "Hardcoded secret", 8),
+    ];
+
+    let mut baseline = Baseline::from_findings(&findings, root, None);
+    baseline.entries[1].expires = Some("2099-01-31".to_string());
+    baseline.save(root, &mut common::journal(root)).unwrap();
+    let loaded = Baseline::load(root).unwrap().unwrap();
+    assert_eq!(loaded.entries[0].expires, None);
+    assert_eq!(loaded.entries[1].expires.as_deref(), Some("2099-01-31"));
+
+    // A fresh baseline of the same findings, with lines moved
+    let moved = vec![
+        make_finding(&file, "Hardcoded secret", 20),
+        make_finding(&file, "Weak hash", 4),
+    ];
+    let mut rebuilt = Baseline::from_findings(&moved, root, None);
+    rebuilt.keep_expiry_from(&loaded);
+    assert_eq!(rebuilt.entries[0].expires.as_deref(), Some("2099-01-31"));
+    assert_eq!(rebuilt.entries[1].expires, None);
+}
+
+#[test]
+fn test_entry_statuses() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(root.join("src/main.py"), "x = 1\n").unwrap();
+    let file = root.join("src/main.py").to_string_lossy().to_string();
+    let gone = root.join("src/gone.py").to_string_lossy().to_string();
+
+    let baseline = Baseline::from_findings(
+        &[
+            make_finding(&file, "Weak hash", 3),
+            make_finding(&file, "Fixed since", 5),
+            make_finding(&gone, "Weak hash", 1),
+        ],
+        root,
+        None,
+    );
+    let findings = vec![
+        make_finding(&file, "Something new", 1),
+        make_finding(&file, "Weak hash", 9),
+    ];
+
+    let statuses = entry_statuses(&baseline, &findings, root);
+    let summary: Vec<(EntryStatus, Option<usize>)> =
+        statuses.iter().map(|m| (m.status, m.finding)).collect();
+    assert_eq!(
+        summary,
+        vec![
+            (EntryStatus::Matched, Some(1)),
+            (EntryStatus::Unmatched, None),
+            (EntryStatus::FileDeleted, None),
+        ]
+    );
+}
+
+#[test]
+fn test_is_date() {
+    assert!(is_date("2030-01-31"));
+    assert!(is_date("2028-02-29"));
+    assert!(!is_date("2027-02-29"));
+    assert!(!is_date("2100-02-29"));
+    assert!(!is_date("2030-13-01"));
+    assert!(!is_date("2030-1-31"));
+    assert!(!is_date("31/01/2030"));
+    assert!(is_date(&baseline::today()));
+}
@@ -0,0 +1,202 @@
+use revet_core::analyzer::ownership::codeowners::CodeOwners;
+use revet_core::baseline::delta::{self, DebtState, Delta, OwnerDelta, Snapshot, UNOWNED};
+use revet_core::{Baseline, BaselineEntry, Finding, Severity};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const TODAY: &str = "2026-06-01";
+
+const CODEOWNERS: &str = "\
+/api/ @acme/api alice
+/web/ bob @acme/web
+/docs/ carol
+";
+
+fn finding(root: &Path, file: &str, message: &str) -> Finding {
+    Finding {
+        id: "SEC-001".to_string(),
+        severity: Severity::Warning,
+        message: message.to_string(),
+        file: root.join(file),
+        line: 1,
+        ..Default::default()
+    }
+}
+
+/// A repository with every file in `files`
+fn repo(files: &[&str]) -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    for file in files {
+        let path = tmp.path().join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "x = 1\n").unwrap();
+    }
+    tmp
+}
+
+fn owner<'a>(delta: &'a Delta, owner: &str) -> &'a OwnerDelta {
+    delta
+        .owners
+        .iter()
+        .find(|d| d.owner == owner)
+        .unwrap_or_else(|| panic!("no delta for {}", owner))
+}
+
+#[test]
+fn test_snapshot_states() {
+    let tmp = repo(&["api/a.py", "api/b.py"]);
+    let root = tmp.path();
+    let findings = vec![
+        finding(root, "api/a.py", "live"),
+        finding(root, "api/a.py", "expired"),
+    ];
+    let mut baseline = Baseline::from_findings(
+        &[
+            findings[0].clone(),
+            findings[1].clone(),
+            finding(root, "api/b.py", "fixed"),
+            finding(root, "api/gone.py", "deleted"),
+        ],
+        root,
+        None,
+    );
+    baseline.entries[1].expires = Some("2026-01-01".to_string());
+
+    let snapshot = Snapshot::of(&baseline, &findings, root, TODAY);
+    let states: Vec<DebtState> = snapshot.entries.iter().map(|e| e.state).collect();
+    assert_eq!(
+        states,
+        [
+            DebtState::Live,
+            DebtState::Expired,
+            DebtState::Fixed,
+            DebtState::Fixed
+        ]
+    );
+    assert_eq!(snapshot.live(), 1);
+}
+
+#[test]
+fn test_delta_over_scripted_runs() {
+    let tmp = repo(&["api/a.py", "web/b.py", "docs/c.py", "lib/d.py"]);
+    let root = tmp.path();
+    let owners = CodeOwners::parse(CODEOWNERS);
+    let run =
+        |baseline: &Baseline, findings: &[Finding]| Snapshot::of(baseline, findings, root, TODAY);
+
+    // Run 1: four entries, all live
+    let first_findings = vec![
+        finding(root, "api/a.py", "key"),
+        finding(root, "web/b.py", "eval"),
+        finding(root, "docs/c.py", "token"),
+        finding(root, "lib/d.py", "shell"),
+    ];
+    let mut baseline = Baseline::from_findings(&first_findings, root, None);
+    let first = run(&baseline, &first_findings);
+    assert_eq!(first.live(), 4);
+
+    // Run 2: the web finding is fixed, the api entry expires, and a new
+    // api finding is baselined
+    let mut second_findings = vec![
+        finding(root, "api/a.py", "key"),
+        finding(root, "docs/c.py", "token"),
+        finding(root, "lib/d.py", "shell"),
+        finding(root, "api/a.py", "password"),
+    ];
+    baseline.entries[0].expires = Some("2026-05-01".to_string());
+    baseline
+        .entries
+        .push(BaselineEntry::of(&second_findings[3], root));
+    let second = run(&baseline, &second_findings);
+    let delta = Delta::between(&first, &second, &owners);
+
+    let api = owner(&delta, "@acme/api");
+    assert_eq!((api.added, api.removed, api.expired), (1, 0, 1));
+    assert_eq!(api.net(), 0);
+    let web = owner(&delta, "@acme/web");
+    assert_eq!((web.added, web.removed, web.expired), (0, 1, 0));
+    assert!(delta.owners.iter().all(|d| d.owner != "carol"));
+    assert_eq!(
+        delta.total().net(),
+        second.live() as i64 - first.live() as i64
+    );
+
+    // Run 3: the docs entry is dropped from the baseline, the unowned
+    // finding is fixed
+    second_findings.retain(|f| !f.file.ends_with("lib/d.py"));
+    baseline.entries.retain(|e| e.file != "docs/c.py");
+    let third = run(&baseline, &second_findings);
+    let next = Delta::between(&second, &third, &owners);
+    assert_eq!(owner(&next, "carol").removed, 1);
+    assert_eq!(owner(&next, UNOWNED).removed, 1);
+    assert_eq!(
+        next.total().net(),
+        third.live() as i64 - second.live() as i64
+    );
+
+    // An unchanged run changes nothing
+    assert!(Delta::between(&third, &third, &owners).is_empty());
+
+    // The deltas add up to the change over the whole sequence
+    let mut sum = delta.clone();
+    sum.add(&next);
+    assert_eq!(sum.total().net(), third.live() as i64 - first.live() as i64);
+    assert_eq!(owner(&sum, "@acme/api").added, 1);
+    assert_eq!(owner(&sum, "@acme/web").removed, 1);
+}
+
+#[test]
+fn test_delta_dropped_counts_removed() {
+    let owners = CodeOwners::parse(CODEOWNERS);
+    let entry = |file: &str| BaselineEntry {
+        file: file.to_string(),
+        message: "m".to_string(),
+        rule: None,
+        source: None,
+        generated: false,
+        expires: None,
+        line: None,
+    };
+    let delta = Delta::dropped(
+        &[entry("api/a.py"), entry("api/b.py"), entry("x.py")],
+        &owners,
+    );
+    assert_eq!(owner(&delta, "@acme/api").removed, 2);
+    assert_eq!(owner(&delta, UNOWNED).removed, 1);
+    assert_eq!(delta.total().net(), -3);
+}
+
+#[test]
+fn test_owner_of_prefers_team() {
+    let owners = CodeOwners::parse(CODEOWNERS);
+    assert_eq!(delta::owner_of(&owners, "api/a.py"), "@acme/api");
+    assert_eq!(delta::owner_of(&owners, "web/b.py"), "@acme/web");
+    assert_eq!(delta::owner_of(&owners, "docs/c.py"), "carol");
+    assert_eq!(delta::owner_of(&owners, "lib/d.py"), UNOWNED);
+}
+
+#[test]
+fn test_owner_delta_serializes_net() {
+    let delta = OwnerDelta {
+        owner: "@acme/api".to_string(),
+        added: 1,
+        removed: 3,
+        expired: 0,
+    };
+    let json = serde_json::to_value(&delta).unwrap();
+    assert_eq!(json["net"], -2);
+    let back: OwnerDelta = serde_json::from_value(json).unwrap();
+    assert_eq!(back, delta);
+}
+
+#[test]
+fn test_entry_key_ignores_line_and_expiry() {
+    let root = PathBuf::from("/repo");
+    let mut entry = BaselineEntry::of(&finding(&root, "api/a.py", "key"), &root);
+    let key = delta::entry_key(&entry);
+    entry.line = Some(99);
+    entry.expires = Some("2030-01-01".to_string());
+    assert_eq!(delta::entry_key(&entry), key);
+    entry.message = "other".to_string();
+    assert_ne!(delta::entry_key(&entry), key);
+}
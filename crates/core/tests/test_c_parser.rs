@@ -270,7 +270,7 @@ fn test_c_file_node_language() {
         .find(|(_, n)| matches!(n.kind(), NodeKind::File))
         .expect("File node not found");
 
-    if let NodeData::File { language } = file_node.1.data() {
+    if let NodeData::File { language, .. } = file_node.1.data() {
         assert_eq!(language, "c");
     } else {
         panic!("Expected File data");
@@ -350,7 +350,7 @@ fn test_cpp_file_node_language() {
         .find(|(_, n)| matches!(n.kind(), NodeKind::File))
         .expect("File node not found");
 
-    if let NodeData::File { language } = file_node.1.data() {
+    if let NodeData::File { language, .. } = file_node.1.data() {
         assert_eq!(language, "cpp");
     } else {
         panic!("Expected File data");
@@ -1,9 +1,9 @@
 //! Tests for graph caching
 
 use revet_core::graph::{CodeGraph, Node, NodeData, NodeKind};
-use revet_core::{GraphCache, GraphCacheMeta};
+use revet_core::{FileGraphCache, GraphCache, GraphCacheMeta, ParserDispatcher};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use tempfile::TempDir;
 
@@ -99,6 +99,11 @@ fn test_file_checksum_computation() {
 
     // Same file should have same checksum
     assert_eq!(checksum1, checksum2);
+    // The SHA-256 of the contents, the same on every toolchain
+    assert_eq!(
+        checksum1,
+        "dffd6021bb2bd5b0af676290809ec3a53191dd81c7f70a4b28688a362182986f"
+    );
 
     // Modify file
     std::fs::write(&file_path, "Hello, Rust!").unwrap();
@@ -206,3 +211,191 @@ fn test_cache_version_validation() {
     // Cache should be invalid due to version mismatch
     assert!(!cache.is_cache_valid(&meta).unwrap());
 }
+
+/// A tree of `count` Python modules, each calling into the previous one
+fn python_tree(root: &Path, count: usize) -> Vec<PathBuf> {
+    (0..count)
+        .map(|i| {
+            let path = root.join(format!("mod_{}.py", i));
+            let import = match i {
+                0 => String::new(),
+                _ => format!("from mod_{} import f{}\n", i - 1, i - 1),
+            };
+            std::fs::write(
+                &path,
+                format!(
+                    "{}\n\ndef f{}(x):\n    return x + {}\n\n\nclass C{}:\n    def m(self):\n        return f{}(1)\n",
+                    import, i, i, i, i
+                ),
+            )
+            .unwrap();
+            path
+        })
+        .collect()
+}
+
+fn edge_count(graph: &CodeGraph) -> usize {
+    graph
+        .nodes()
+        .map(|(id, _)| graph.edges_from(id).count())
+        .sum()
+}
+
+fn cached_files(repo: &Path) -> usize {
+    std::fs::read_dir(repo.join(".revet-cache/files"))
+        .map(|entries| entries.count())
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_second_run_parses_no_files_on_an_unchanged_tree() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    let files = python_tree(&root, 50);
+    let dispatcher = ParserDispatcher::new();
+
+    let (first, _, cached, parsed) =
+        dispatcher.parse_files_incremental(&files, root.clone(), &FileGraphCache::new(&root));
+    assert_eq!((cached, parsed), (0, 50));
+    assert_eq!(cached_files(&root), 50);
+
+    // Every file is a cache hit: none is parsed and no fragment is written
+    let (second, errors, cached, parsed) =
+        dispatcher.parse_files_incremental(&files, root.clone(), &FileGraphCache::new(&root));
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!((cached, parsed), (50, 0));
+    assert_eq!(cached_files(&root), 50);
+    assert_eq!(second.nodes().count(), first.nodes().count());
+    assert_eq!(edge_count(&second), edge_count(&first));
+
+    // An edit re-parses that file alone
+    std::fs::write(&files[7], "def f7(x):\n    return x\n").unwrap();
+    let (_, _, cached, parsed) =
+        dispatcher.parse_files_incremental(&files, root.clone(), &FileGraphCache::new(&root));
+    assert_eq!((cached, parsed), (49, 1));
+}
+
+#[test]
+fn test_unused_fragments_are_evicted() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    let files = python_tree(&root, 5);
+    let dispatcher = ParserDispatcher::new();
+    dispatcher.parse_files_incremental(&files, root.clone(), &FileGraphCache::new(&root));
+    assert_eq!(cached_files(&root), 5);
+
+    // One file deleted, one edited: their old fragments go, the edit's stays
+    std::fs::remove_file(&files[0]).unwrap();
+    std::fs::write(&files[1], "def f1(x):\n    return x\n").unwrap();
+    let cache = FileGraphCache::new(&root);
+    let (_, _, cached, parsed) =
+        dispatcher.parse_files_incremental(&files[1..], root.clone(), &cache);
+    assert_eq!((cached, parsed), (3, 1));
+    assert_eq!(cached_files(&root), 6);
+    // An entry of an older format is never read, and goes with them
+    let old = root.join(".revet-cache/files/0123456789abcdef.msgpack");
+    std::fs::write(&old, b"stale").unwrap();
+    assert_eq!(cache.evict_unused(), 3);
+    assert!(!old.exists());
+    assert_eq!(cached_files(&root), 4);
+
+    let (_, _, cached, parsed) =
+        dispatcher.parse_files_incremental(&files[1..], root.clone(), &FileGraphCache::new(&root));
+    assert_eq!((cached, parsed), (4, 0));
+}
+
+#[test]
+fn test_disabled_file_cache_parses_everything() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    let files = python_tree(&root, 3);
+    let dispatcher = ParserDispatcher::new();
+    dispatcher.parse_files_incremental(&files, root.clone(), &FileGraphCache::new(&root));
+
+    let cache = FileGraphCache::new(&root).enabled(false);
+    let (_, _, cached, parsed) = dispatcher.parse_files_incremental(&files, root.clone(), &cache);
+    assert_eq!((cached, parsed), (0, 3));
+    // Neither read nor cleaned up
+    assert_eq!(cache.evict_unused(), 0);
+    assert_eq!(cached_files(&root), 3);
+}
+
+#[test]
+fn test_identical_files_keep_their_own_paths() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    let source = "def setup():\n    return 1\n\n\nclass Config:\n    pass\n";
+    let files: Vec<PathBuf> = ["app/__init__.py", "tests/__init__.py"]
+        .iter()
+        .map(|name| {
+            let path = root.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, source).unwrap();
+            path
+        })
+        .collect();
+    let dispatcher = ParserDispatcher::new();
+
+    // Cold, then warm from the fragments of the first run
+    for expected in [(0, 2), (2, 0)] {
+        let (graph, _, cached, parsed) =
+            dispatcher.parse_files_incremental(&files, root.clone(), &FileGraphCache::new(&root));
+        assert_eq!((cached, parsed), expected);
+        for file in &files {
+            let own: Vec<_> = graph
+                .nodes()
+                .filter(|(_, n)| n.file_path() == file)
+                .map(|(_, n)| (n.name().to_string(), *n.kind()))
+                .collect();
+            assert_eq!(own.len(), 3, "{}: {:?}", file.display(), own);
+            assert!(own.contains(&("__init__.py".to_string(), NodeKind::File)));
+        }
+        assert!(graph.nodes().all(|(_, n)| files.contains(n.file_path())));
+    }
+}
+
+#[test]
+fn test_fragment_is_reattached_to_the_file_loaded() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    let files = python_tree(&root, 2);
+    let dispatcher = ParserDispatcher::new();
+    let cache = FileGraphCache::new(&root);
+    dispatcher.parse_files_incremental(&files, root.clone(), &cache);
+
+    // The same entry read for another path points at that path
+    let key = FileGraphCache::key(&root, &files[1]).unwrap();
+    let other = root.join("copy/mod_1.py");
+    let (graph, state) = cache.load(&key, &root, &other).unwrap();
+    assert!(graph.nodes().all(|(_, n)| n.file_path() == &other));
+    assert_eq!(state.source_file.as_ref(), Some(&other));
+    assert!(!state.unresolved_imports.is_empty());
+    assert!(state
+        .unresolved_imports
+        .iter()
+        .all(|i| i.importing_file == other));
+
+    // The key covers the path as well as the content
+    std::fs::create_dir_all(other.parent().unwrap()).unwrap();
+    std::fs::copy(&files[1], &other).unwrap();
+    assert_ne!(FileGraphCache::key(&root, &other).unwrap(), key);
+}
+
+#[test]
+fn test_read_only_file_cache_writes_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    let files = python_tree(&root, 3);
+    let dispatcher = ParserDispatcher::new();
+
+    let cache = FileGraphCache::new(&root).read_only(true);
+    let (_, _, cached, parsed) = dispatcher.parse_files_incremental(&files, root.clone(), &cache);
+    assert_eq!((cached, parsed), (0, 3));
+    assert!(!root.join(".revet-cache").exists());
+
+    // Fragments written by another run are still read
+    dispatcher.parse_files_incremental(&files[..2], root.clone(), &FileGraphCache::new(&root));
+    let (_, _, cached, parsed) = dispatcher.parse_files_incremental(&files, root.clone(), &cache);
+    assert_eq!((cached, parsed), (2, 1));
+    assert_eq!(cached_files(&root), 2);
+}
@@ -0,0 +1,310 @@
+//! Tests for `revet calibrate` measurement: fixtures with known metric
+//! distributions, and suggested thresholds that produce exactly the
+//! budgeted number of findings when applied
+
+use revet_core::calibrate::{toml_section, Budget, Metric};
+use revet_core::{AnalyzerDispatcher, CodeGraph, Finding, ParserDispatcher, RevetConfig};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn write_file(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+    let path = dir.path().join(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn metric<'a>(metrics: &'a [Metric], setting: &str) -> &'a Metric {
+    metrics
+        .iter()
+        .find(|m| m.setting == setting)
+        .unwrap_or_else(|| panic!("no {} metric in {:?}", setting, metrics))
+}
+
+// ── Complexity ──────────────────────────────────────────────────────────
+
+/// Twenty functions of cyclomatic complexity 1 to 20
+fn complexity_fixture() -> (TempDir, CodeGraph, RevetConfig) {
+    let dir = TempDir::new().unwrap();
+    let mut src = String::new();
+    for k in 1..=20 {
+        src.push_str(&format!("def f{}(x):\n", k));
+        for _ in 1..k {
+            src.push_str("    if x > 0: x -= 1\n");
+        }
+        src.push_str("    return x\n\n");
+    }
+    let file = write_file(&dir, "funcs.py", &src);
+    let mut graph = CodeGraph::new(dir.path().to_path_buf());
+    ParserDispatcher::new()
+        .parse_file(&file, &mut graph)
+        .unwrap();
+
+    let mut config = RevetConfig::default();
+    config.modules.complexity = true;
+    (dir, graph, config)
+}
+
+fn cyclomatic_findings(graph: &CodeGraph, config: &RevetConfig) -> usize {
+    AnalyzerDispatcher::new_with_config(config)
+        .run_graph_analyzers(graph, config)
+        .iter()
+        .filter(|f| f.message.contains("cyclomatic complexity"))
+        .count()
+}
+
+#[test]
+fn test_complexity_measures_every_function() {
+    let (dir, graph, config) = complexity_fixture();
+    let metrics = AnalyzerDispatcher::new_with_config(&config).measure(
+        &[],
+        Some(&graph),
+        dir.path(),
+        &config,
+    );
+    let complexity = metric(&metrics, "modules.complexity_threshold");
+
+    let expected: Vec<f64> = (1..=20).map(|k| k as f64).collect();
+    assert_eq!(complexity.values, expected);
+    assert_eq!(complexity.current, 10.0);
+    assert_eq!(complexity.current_findings(), Some(11));
+    assert_eq!(complexity.percentile(50), Some(10.0));
+    assert_eq!(complexity.percentile(95), Some(19.0));
+    // one past the largest value flags nothing
+    assert_eq!(complexity.candidates.last(), Some(&(21.0, 0)));
+}
+
+#[test]
+fn test_complexity_suggestions_hit_the_budget_exactly() {
+    let (dir, graph, mut config) = complexity_fixture();
+    let metrics = AnalyzerDispatcher::new_with_config(&config).measure(
+        &[],
+        Some(&graph),
+        dir.path(),
+        &config,
+    );
+    let complexity = metric(&metrics, "modules.complexity_threshold").clone();
+
+    // 90th percentile of 20 functions: at most 2 findings
+    for (budget, threshold, findings) in [
+        (Budget::Percentile(90), 19.0, 2),
+        (Budget::Percentile(75), 16.0, 5),
+        (Budget::MaxFindings(7), 14.0, 7),
+        (Budget::MaxFindings(0), 21.0, 0),
+    ] {
+        assert_eq!(
+            complexity.suggest(&budget),
+            Some((threshold, findings)),
+            "{:?}",
+            budget
+        );
+        config.modules.complexity_threshold = threshold as usize;
+        assert_eq!(
+            cyclomatic_findings(&graph, &config),
+            findings,
+            "{:?}",
+            budget
+        );
+    }
+}
+
+// ── Duplication ─────────────────────────────────────────────────────────
+
+/// `n` lines unique to `tag`
+fn unique_lines(tag: &str, n: usize) -> String {
+    (0..n)
+        .map(|i| format!("{}_v{} = compute_{}_{}(arg)\n", tag, i, tag, i))
+        .collect()
+}
+
+/// One 8-line block shared by two files, one 5-line block shared by two
+/// others
+fn duplication_fixture() -> (TempDir, Vec<PathBuf>, RevetConfig) {
+    let dir = TempDir::new().unwrap();
+    let long = unique_lines("shared", 8);
+    let short = unique_lines("common", 5);
+    let files = vec![
+        write_file(
+            &dir,
+            "a.py",
+            &format!("{}{}{}", unique_lines("a", 3), long, unique_lines("a2", 3)),
+        ),
+        write_file(
+            &dir,
+            "b.py",
+            &format!("{}{}{}", unique_lines("b", 2), long, unique_lines("b2", 4)),
+        ),
+        write_file(&dir, "c.py", &format!("{}{}", short, unique_lines("c", 3))),
+        write_file(&dir, "d.py", &format!("{}{}", unique_lines("d", 1), short)),
+    ];
+    let mut config = RevetConfig::default();
+    config.modules.duplication = true;
+    (dir, files, config)
+}
+
+fn duplicates(files: &[PathBuf], root: &Path, config: &RevetConfig) -> Vec<Finding> {
+    AnalyzerDispatcher::new_with_config(config)
+        .run_all(files, root, config)
+        .into_iter()
+        .filter(|f| f.id.starts_with("DUP-"))
+        .collect()
+}
+
+#[test]
+fn test_duplication_measures_clone_sizes() {
+    let (dir, files, config) = duplication_fixture();
+    let metrics =
+        AnalyzerDispatcher::new_with_config(&config).measure(&files, None, dir.path(), &config);
+    let duplication = metric(&metrics, "modules.duplication_min_lines");
+
+    // Each start line of a shared block counts once, at the longest block
+    // still reported from it: 3..=8 for the long block, 3..=5 for the short
+    assert_eq!(
+        duplication.values,
+        vec![3.0, 3.0, 4.0, 4.0, 5.0, 5.0, 6.0, 7.0, 8.0]
+    );
+    assert_eq!(
+        duplication.candidates,
+        vec![
+            (3.0, 9),
+            (4.0, 7),
+            (5.0, 5),
+            (6.0, 3),
+            (7.0, 2),
+            (8.0, 1),
+            (9.0, 0)
+        ]
+    );
+    assert_eq!(duplication.current_findings(), Some(3));
+}
+
+#[test]
+fn test_duplication_suggestions_hit_the_budget_exactly() {
+    let (dir, files, mut config) = duplication_fixture();
+    let metrics =
+        AnalyzerDispatcher::new_with_config(&config).measure(&files, None, dir.path(), &config);
+    let duplication = metric(&metrics, "modules.duplication_min_lines").clone();
+
+    for (budget, threshold, findings) in [
+        (Budget::MaxFindings(2), 7.0, 2),
+        (Budget::MaxFindings(5), 5.0, 5),
+        // 9 clone starts above the 50th percentile: at most 4 findings
+        (Budget::Percentile(50), 6.0, 3),
+    ] {
+        assert_eq!(
+            duplication.suggest(&budget),
+            Some((threshold, findings)),
+            "{:?}",
+            budget
+        );
+        config.modules.duplication_min_lines = threshold as usize;
+        assert_eq!(
+            duplicates(&files, dir.path(), &config).len(),
+            findings,
+            "{:?}",
+            budget
+        );
+    }
+}
+
+// ── Secret entropy ──────────────────────────────────────────────────────
+
+const TOKEN_PATTERN: &str = r#"
+[[analyzers.secrets.patterns]]
+name = "acme"
+regex = 'acme_tok_(?P<secret>[a-p]+)'
+severity = "error"
+entropy_check = true
+"#;
+
+/// Candidate values of 0, 1, 2, 3 and 4 bits per character
+fn entropy_fixture() -> (TempDir, Vec<PathBuf>, RevetConfig) {
+    let dir = TempDir::new().unwrap();
+    let values = [
+        "aaaaaaaaaaaaaaaa",
+        "abababababababab",
+        "abcdabcdabcdabcd",
+        "abcdefghabcdefgh",
+        "abcdefghijklmnop",
+    ];
+    let src: String = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| format!("token_{} = \"acme_tok_{}\"\n", i, v))
+        .collect();
+    let files = vec![write_file(&dir, "tokens.py", &src)];
+    let config: RevetConfig = toml::from_str(TOKEN_PATTERN).unwrap();
+    (dir, files, config)
+}
+
+#[test]
+fn test_entropy_suggestions_hit_the_budget_exactly() {
+    let (dir, files, mut config) = entropy_fixture();
+    let metrics =
+        AnalyzerDispatcher::new_with_config(&config).measure(&files, None, dir.path(), &config);
+    let entropy = metric(&metrics, "analyzers.secrets.min_entropy").clone();
+    assert_eq!(entropy.values, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(entropy.current_findings(), Some(2));
+
+    for (budget, threshold, findings) in [
+        (Budget::MaxFindings(3), 2.0, 3),
+        (Budget::MaxFindings(1), 4.0, 1),
+        // 5 values above the 80th percentile: at most 1 finding
+        (Budget::Percentile(80), 4.0, 1),
+    ] {
+        assert_eq!(
+            entropy.suggest(&budget),
+            Some((threshold, findings)),
+            "{:?}",
+            budget
+        );
+        config.analyzers.secrets.min_entropy = threshold;
+        let sec = AnalyzerDispatcher::new_with_config(&config)
+            .run_all(&files, dir.path(), &config)
+            .into_iter()
+            .filter(|f| f.rule.as_deref() == Some("security:custom-acme"))
+            .count();
+        assert_eq!(sec, findings, "{:?}", budget);
+    }
+}
+
+#[test]
+fn test_entropy_is_not_measured_without_entropy_checked_patterns() {
+    let dir = TempDir::new().unwrap();
+    let files = vec![write_file(&dir, "a.py", "x = 1\n")];
+    let config = RevetConfig::default();
+    let metrics =
+        AnalyzerDispatcher::new_with_config(&config).measure(&files, None, dir.path(), &config);
+    assert!(metrics
+        .iter()
+        .all(|m| m.setting != "analyzers.secrets.min_entropy"));
+}
+
+// ── Suggested section ───────────────────────────────────────────────────
+
+#[test]
+fn test_toml_section_parses_and_comments_out_suggestions() {
+    let (dir, graph, config) = complexity_fixture();
+    let metrics = AnalyzerDispatcher::new_with_config(&config).measure(
+        &[],
+        Some(&graph),
+        dir.path(),
+        &config,
+    );
+    let section = toml_section(&metrics, &Budget::MaxFindings(5));
+
+    assert!(
+        section.contains("# [modules]\n# complexity_threshold = 16\n"),
+        "{}",
+        section
+    );
+    assert!(
+        section.contains("# 5 finding(s) at 16 (currently 10: 11 finding(s))"),
+        "{}",
+        section
+    );
+    // Pasted as is, only the budget takes effect
+    let pasted: RevetConfig = toml::from_str(&section).unwrap();
+    assert_eq!(pasted.calibration.max_findings, Some(5));
+    assert_eq!(pasted.modules.complexity_threshold, 10);
+    assert!(pasted.validate().0.is_empty());
+}
@@ -0,0 +1,68 @@
+//! Tests for cooperative cancellation of parsing and analysis
+
+use revet_core::{AnalyzerDispatcher, CancelToken, Cancelled, ParserDispatcher, RevetConfig};
+use std::path::{Path, PathBuf};
+
+fn fixture() -> (PathBuf, Vec<PathBuf>) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures/python_flask_app")
+        .canonicalize()
+        .unwrap();
+    let files = revet_core::discover_files(&root, &[".py"], &[]).unwrap();
+    assert!(!files.is_empty());
+    (root, files)
+}
+
+#[test]
+fn test_token_is_shared_between_clones() {
+    let token = CancelToken::new();
+    let clone = token.clone();
+    assert_eq!(token.check(), Ok(()));
+    clone.cancel();
+    assert!(token.is_cancelled());
+    assert_eq!(token.check(), Err(Cancelled));
+}
+
+#[test]
+fn test_live_token_matches_uncancellable_run() {
+    let (root, files) = fixture();
+    let config = RevetConfig::default();
+    let token = CancelToken::new();
+
+    let parsers = ParserDispatcher::new();
+    let (graph, errors) = parsers.parse_files_parallel(&files, root.clone());
+    let (cancellable, cancellable_errors) = parsers
+        .parse_files_parallel_cancellable(&files, root.clone(), &token)
+        .unwrap();
+    assert_eq!(graph.nodes().count(), cancellable.nodes().count());
+    assert_eq!(errors, cancellable_errors);
+
+    let analyzers = AnalyzerDispatcher::new_with_config(&config);
+    let ids = |findings: Vec<revet_core::Finding>| -> Vec<String> {
+        findings.into_iter().map(|f| f.id).collect()
+    };
+    assert_eq!(
+        ids(analyzers.run_all_parallel(&files, &root, &config)),
+        ids(analyzers
+            .run_all_parallel_cancellable(&files, &root, &config, &token)
+            .unwrap())
+    );
+}
+
+#[test]
+fn test_cancelled_token_stops_parsing_and_analysis() {
+    let (root, files) = fixture();
+    let config = RevetConfig::default();
+    let token = CancelToken::new();
+    token.cancel();
+
+    assert!(ParserDispatcher::new()
+        .parse_files_parallel_cancellable(&files, root.clone(), &token)
+        .is_err());
+    assert_eq!(
+        AnalyzerDispatcher::new_with_config(&config)
+            .run_all_parallel_cancellable(&files, &root, &config, &token)
+            .unwrap_err(),
+        Cancelled
+    );
+}
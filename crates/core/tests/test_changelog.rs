@@ -0,0 +1,133 @@
+//! Tests for release notes between two graph snapshots
+//! (`revet snapshot changelog`)
+
+use revet_core::config::RevetConfig;
+use revet_core::diff::changelog::{Changelog, ChangelogOptions, Projects, SymbolFilter};
+use revet_core::discovery::discover_files;
+use revet_core::store::{reconstruct_graph, GraphStore, MemoryStore};
+use revet_core::{CodeGraph, ParserDispatcher};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/changelog")
+}
+
+fn copy_tree(from: &Path, to: &Path) {
+    for entry in std::fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            std::fs::create_dir_all(&target).unwrap();
+            copy_tree(&entry.path(), &target);
+        } else {
+            std::fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+fn parse(root: &Path) -> CodeGraph {
+    let dispatcher = ParserDispatcher::new_with_config(&RevetConfig::default());
+    let files = discover_files(root, &dispatcher.supported_extensions(), &[]).unwrap();
+    let (graph, errors) = dispatcher.parse_files_parallel(&files, root.to_path_buf());
+    assert!(errors.is_empty(), "errors: {:?}", errors);
+    graph
+}
+
+/// `old/` and `new/` flushed as snapshots `v1` and `v2` and read back, each
+/// copied out of the repository's `tests/` directory so its application
+/// code is not classified as tests
+fn changelog(options: &ChangelogOptions) -> Changelog {
+    let store = MemoryStore::new();
+    let mut trees = Vec::new();
+    for (side, snapshot) in [("old", "v1"), ("new", "v2")] {
+        let dir = TempDir::new().unwrap();
+        copy_tree(&fixture_dir().join(side), dir.path());
+        store.flush(&parse(dir.path()), snapshot).unwrap();
+        let graph = reconstruct_graph(&store, snapshot, dir.path()).unwrap();
+        trees.push((dir, graph));
+    }
+    let (old, new) = (&trees[0], &trees[1]);
+    Changelog::between(
+        "v1",
+        &old.1,
+        "v2",
+        &new.1,
+        options,
+        &Projects::new(new.0.path()),
+    )
+}
+
+fn golden(name: &str) -> String {
+    std::fs::read_to_string(fixture_dir().join(name)).unwrap()
+}
+
+#[test]
+fn test_markdown_matches_golden() {
+    let changelog = changelog(&ChangelogOptions::default());
+    assert_eq!(changelog.to_markdown(), golden("expected.md"));
+}
+
+#[test]
+fn test_public_only_json_matches_golden() {
+    let changelog = changelog(&ChangelogOptions {
+        filter: SymbolFilter::PublicOnly,
+        ..Default::default()
+    });
+    assert_eq!(
+        format!("{}\n", changelog.to_json().unwrap()),
+        golden("expected.json")
+    );
+}
+
+#[test]
+fn test_matched_symbols_are_reported_once() {
+    let changelog = changelog(&ChangelogOptions::default());
+    let root = &changelog.projects[0];
+    assert_eq!(root.name, ".");
+
+    let mut names: Vec<&str> = root
+        .added
+        .iter()
+        .chain(&root.removed)
+        .map(|s| s.name.as_str())
+        .chain(root.moved.iter().map(|m| m.symbol.name.as_str()))
+        .chain(root.renamed.iter().map(|r| r.symbol.name.as_str()))
+        .chain(root.renamed.iter().map(|r| r.old_name.as_str()))
+        .collect();
+    let total = names.len();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), total, "{:?}", names);
+    assert_eq!(root.moved[0].symbol.name, "slugify");
+    assert_eq!(root.renamed[0].old_name, "parse_header");
+}
+
+#[test]
+fn test_identical_snapshots_have_no_changes() {
+    let dir = TempDir::new().unwrap();
+    copy_tree(&fixture_dir().join("new"), dir.path());
+    let graph = parse(dir.path());
+    let changelog = Changelog::between(
+        "a",
+        &graph,
+        "b",
+        &graph,
+        &ChangelogOptions::default(),
+        &Projects::new(dir.path()),
+    );
+    assert!(changelog.is_empty());
+    assert_eq!(
+        changelog.to_markdown(),
+        "# API changes: a → b\n\nNo API changes.\n"
+    );
+}
+
+#[test]
+fn test_churn_threshold() {
+    let strict = changelog(&ChangelogOptions {
+        churn_threshold: 100,
+        ..Default::default()
+    });
+    assert!(strict.projects.iter().all(|p| p.churn.is_empty()));
+}
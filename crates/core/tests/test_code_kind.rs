@@ -0,0 +1,244 @@
+//! Tests for production/test/script classification and `[files]` code kinds
+
+use revet_core::code_kind::classify;
+use revet_core::config::FilesConfig;
+use revet_core::{CodeKind, CodeKinds, Finding, RevetConfig, Severity};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn kind(path: &str, source: &str) -> CodeKind {
+    classify(Path::new(path), Path::new(""), source)
+}
+
+// ── Built-in conventions ─────────────────────────────────────────
+
+#[test]
+fn test_test_paths_and_names() {
+    let tests = [
+        "tests/orders.py",
+        "src/__tests__/cart.tsx",
+        "spec/models/order_spec.rb",
+        "pkg/testdata/input.go",
+        "test_orders.py",
+        "conftest.py",
+        "orders_test.go",
+        "cart.test.ts",
+        "cart.spec.js",
+        "checkout.cy.js",
+        "src/test/java/com/example/OrderServiceTest.java",
+        "OrderTests.cs",
+        "OrderSpec.kt",
+        "OrderTest.php",
+    ];
+    for path in tests {
+        assert_eq!(kind(path, ""), CodeKind::Test, "{}", path);
+    }
+}
+
+#[test]
+fn test_script_paths_and_names() {
+    let scripts = [
+        "scripts/release.py",
+        "deploy.sh",
+        "setup.py",
+        "build.rs",
+        "Rakefile",
+        "tools/gulpfile.js",
+    ];
+    for path in scripts {
+        assert_eq!(kind(path, ""), CodeKind::Script, "{}", path);
+    }
+    assert_eq!(
+        kind("bin/migrate", "#!/usr/bin/env python3\nprint(1)\n"),
+        CodeKind::Script
+    );
+}
+
+#[test]
+fn test_production_names_that_look_like_tests() {
+    let production = [
+        "src/testing.py",
+        "src/contest.py",
+        "app/attestation.go",
+        "src/latest.ts",
+        "src/protester.rb",
+        "src/Testimonial.java",
+    ];
+    for path in production {
+        assert_eq!(kind(path, ""), CodeKind::Production, "{}", path);
+    }
+}
+
+#[test]
+fn test_test_content_per_framework() {
+    let cases = [
+        (
+            "checks.py",
+            "import pytest\n\ndef test_a():\n    assert True\n",
+        ),
+        (
+            "checks.py",
+            "import unittest\n\nclass A(unittest.TestCase):\n    pass\n",
+        ),
+        (
+            "checks.ts",
+            "describe(\"a\", () => {\n  it(\"b\", () => expect(1).toBe(1));\n});\n",
+        ),
+        (
+            "Checks.java",
+            "import org.junit.jupiter.api.Test;\n\nclass Checks {\n  @Test\n  void a() {}\n}\n",
+        ),
+        ("checks.rs", "#[test]\nfn a() {\n    assert!(true);\n}\n"),
+        (
+            "checks.rb",
+            "RSpec.describe Order do\n  it \"a\" do\n  end\nend\n",
+        ),
+        (
+            "Checks.cs",
+            "using Xunit;\n\npublic class Checks {\n  [Fact]\n  public void A() {}\n}\n",
+        ),
+        (
+            "Checks.swift",
+            "import XCTest\n\nclass Checks: XCTestCase {}\n",
+        ),
+    ];
+    for (path, source) in cases {
+        assert_eq!(kind(path, source), CodeKind::Test, "{}", path);
+    }
+
+    // Inline unit tests do not make a production file test code
+    assert_eq!(
+        kind(
+            "src/lib.rs",
+            "pub fn a() {}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn b() {}\n}\n"
+        ),
+        CodeKind::Production
+    );
+    // Nor does a function called test_* without a framework
+    assert_eq!(
+        kind("app/retry.py", "def test_connection(call):\n    call()\n"),
+        CodeKind::Production
+    );
+}
+
+#[test]
+fn test_root_is_stripped() {
+    let root = Path::new("/work/tests/repo");
+    assert_eq!(
+        classify(&root.join("app/orders.py"), root, ""),
+        CodeKind::Production
+    );
+    assert_eq!(
+        classify(&root.join("tests/orders.py"), root, ""),
+        CodeKind::Test
+    );
+}
+
+// ── [files] ──────────────────────────────────────────────────────
+
+fn files(toml_str: &str) -> FilesConfig {
+    toml::from_str(toml_str).unwrap()
+}
+
+#[test]
+fn test_configured_globs_take_precedence() {
+    let kinds = CodeKinds::from_config(&files(
+        "test_paths = [\"qa/**\"]\nscript_paths = [\"tests/tools/**\"]\n",
+    ));
+    let root = Path::new("/repo");
+    assert_eq!(
+        kinds.classify(&root.join("qa/smoke.py"), root, ""),
+        CodeKind::Test
+    );
+    assert_eq!(
+        kinds.classify(&root.join("tests/tools/gen.py"), root, ""),
+        CodeKind::Script
+    );
+    assert_eq!(
+        kinds.classify(&root.join("tests/test_a.py"), root, ""),
+        CodeKind::Test
+    );
+}
+
+fn finding(file: PathBuf, severity: Severity) -> Finding {
+    Finding {
+        id: "SEC-001".to_string(),
+        severity,
+        file,
+        line: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_apply_tags_findings_and_caps_severity() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path();
+    std::fs::create_dir_all(root.join("app")).unwrap();
+    std::fs::write(
+        root.join("app/checks.py"),
+        "import pytest\n\ndef test_a():\n    pass\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("app/orders.py"), "def place():\n    pass\n").unwrap();
+
+    let kinds = CodeKinds::from_config(&files(
+        "[max_severity]\ntest = \"info\"\nscript = \"warning\"\n",
+    ));
+    let findings = kinds.apply(
+        vec![
+            finding(root.join("app/orders.py"), Severity::Error),
+            finding(root.join("app/checks.py"), Severity::Error),
+            finding(root.join("deploy.sh"), Severity::Error),
+            finding(root.join("scripts/seed.py"), Severity::Info),
+        ],
+        root,
+    );
+    let tagged: Vec<(CodeKind, Severity)> =
+        findings.iter().map(|f| (f.code_kind, f.severity)).collect();
+    assert_eq!(
+        tagged,
+        vec![
+            (CodeKind::Production, Severity::Error),
+            (CodeKind::Test, Severity::Info),
+            (CodeKind::Script, Severity::Warning),
+            (CodeKind::Script, Severity::Info),
+        ]
+    );
+}
+
+#[test]
+fn test_code_kind_serializes_only_when_not_production() {
+    let mut f = finding(PathBuf::from("a.py"), Severity::Error);
+    let json = serde_json::to_value(&f).unwrap();
+    assert!(json.get("code_kind").is_none());
+    f.code_kind = CodeKind::Test;
+    let json = serde_json::to_value(&f).unwrap();
+    assert_eq!(json["code_kind"], "test");
+}
+
+#[test]
+fn test_config_validation() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[files]
+test_paths = ["qa/[", "tests/**"]
+script_paths = ["tools/**"]
+
+[files.max_severity]
+tests = "info"
+script = "fatal"
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    let errors: Vec<&String> = errors.iter().filter(|e| e.contains("[files")).collect();
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("test_paths entry \"qa/[\" is not a valid glob")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("\"tests\" is not a code kind")));
+    assert!(errors.iter().any(|e| e.contains("script = \"fatal\"")));
+}
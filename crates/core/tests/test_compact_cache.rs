@@ -0,0 +1,442 @@
+//! Tests for the compact binary graph cache format
+
+use revet_core::cache::compact::{self, CompactGraph, FORMAT_VERSION};
+use revet_core::graph::nodes::ModelField;
+use revet_core::graph::{
+    CodeGraph, Doc, Edge, EdgeKind, EdgeMetadata, ImportKind, Node, NodeData, NodeKind, Parameter,
+};
+use revet_core::{CacheFormat, GraphCache, GraphCacheMeta, NodeId, RevetConfig};
+use revet_core::{CodeKind, Confidence};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tempfile::TempDir;
+
+fn meta() -> GraphCacheMeta {
+    GraphCacheMeta {
+        commit_hash: Some("abc123".to_string()),
+        timestamp: SystemTime::now(),
+        file_checksums: HashMap::new(),
+        revet_version: env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+/// One node per `NodeData` variant plus every kind of edge metadata
+fn sample_graph() -> CodeGraph {
+    let mut graph = unfingerprinted_sample_graph();
+    graph.assign_fingerprints();
+    graph
+}
+
+fn unfingerprinted_sample_graph() -> CodeGraph {
+    let mut graph = CodeGraph::new(PathBuf::from("/repo"));
+    let file = PathBuf::from("/repo/src/orders.py");
+    let node =
+        |kind, name: &str, line, data| Node::new(kind, name.to_string(), file.clone(), line, data);
+
+    let f = graph.add_node(node(
+        NodeKind::File,
+        "orders.py",
+        1,
+        NodeData::File {
+            language: "python".to_string(),
+            code_kind: CodeKind::Test,
+        },
+    ));
+    let m = graph.add_node(node(
+        NodeKind::Module,
+        "orders",
+        1,
+        NodeData::Module {
+            exports: vec!["place".to_string(), "cancel".to_string()],
+        },
+    ));
+    let mut place = node(
+        NodeKind::Function,
+        "place",
+        10,
+        NodeData::Function {
+            parameters: vec![
+                Parameter {
+                    name: "order".to_string(),
+                    param_type: Some("Order".to_string()),
+                    default_value: None,
+                },
+                Parameter {
+                    name: "retries".to_string(),
+                    param_type: None,
+                    default_value: Some("3".to_string()),
+                },
+            ],
+            return_type: Some("Receipt".to_string()),
+        },
+    );
+    place.set_end_line(24);
+    place.set_decorators(vec!["transactional".to_string()]);
+    place.set_type_parameters(vec!["T".to_string()]);
+    place.set_is_public(false);
+    place.set_doc(Doc::Present("Place an order.".to_string()));
+    let place = graph.add_node(place);
+    let mut order = node(
+        NodeKind::Class,
+        "Order",
+        30,
+        NodeData::Class {
+            base_classes: vec!["Model".to_string()],
+            methods: vec!["total".to_string()],
+            fields: vec!["id".to_string(), "items".to_string()],
+        },
+    );
+    order.set_doc(Doc::Missing);
+    let c = graph.add_node(order);
+    graph.add_node(node(
+        NodeKind::Interface,
+        "Priced",
+        40,
+        NodeData::Interface {
+            methods: vec!["total".to_string()],
+        },
+    ));
+    graph.add_node(node(
+        NodeKind::Type,
+        "OrderId",
+        45,
+        NodeData::Type {
+            definition: "int".to_string(),
+        },
+    ));
+    graph.add_node(node(
+        NodeKind::Variable,
+        "MAX_ITEMS",
+        46,
+        NodeData::Variable {
+            var_type: Some("int".to_string()),
+            is_constant: true,
+        },
+    ));
+    let i = graph.add_node(node(
+        NodeKind::Import,
+        "billing",
+        2,
+        NodeData::Import {
+            module: "billing".to_string(),
+            imported_names: vec!["charge".to_string()],
+            resolved_path: Some(PathBuf::from("/repo/src/billing.py")),
+        },
+    ));
+    graph.add_node(node(
+        NodeKind::APIEndpoint,
+        "POST /orders",
+        9,
+        NodeData::APIEndpoint {
+            http_method: "POST".to_string(),
+            path: "/orders".to_string(),
+            handler: "place".to_string(),
+        },
+    ));
+    graph.add_node(node(
+        NodeKind::DatabaseModel,
+        "Order",
+        30,
+        NodeData::DatabaseModel {
+            table_name: "orders".to_string(),
+            fields: vec![ModelField {
+                name: "id".to_string(),
+                field_type: "Integer".to_string(),
+                nullable: false,
+            }],
+        },
+    ));
+    graph.add_node(node(
+        NodeKind::ConfigReference,
+        "ORDER_TIMEOUT",
+        12,
+        NodeData::ConfigReference {
+            key: "ORDER_TIMEOUT".to_string(),
+            default_value: None,
+        },
+    ));
+
+    graph.add_edge(f, m, Edge::new(EdgeKind::Contains));
+    graph.add_edge(
+        f,
+        i,
+        Edge::with_metadata(
+            EdgeKind::Imports,
+            EdgeMetadata::Import {
+                alias: Some("b".to_string()),
+                is_wildcard: false,
+                kind: ImportKind::TypeOnly,
+            },
+        ),
+    );
+    graph.add_edge(
+        place,
+        c,
+        Edge::with_metadata(
+            EdgeKind::AcceptsParam,
+            EdgeMetadata::TypeRef {
+                param_index: Some(0),
+            },
+        ),
+    );
+    graph.add_edge(
+        place,
+        c,
+        Edge::with_metadata(
+            EdgeKind::Calls,
+            EdgeMetadata::Call {
+                line: 12,
+                is_direct: true,
+            },
+        ),
+    );
+    graph.add_edge(
+        place,
+        i,
+        Edge::with_metadata(
+            EdgeKind::Calls,
+            EdgeMetadata::ExternalCall {
+                line: 13,
+                callee: "charge".to_string(),
+            },
+        ),
+    );
+    graph.add_edge(
+        m,
+        place,
+        Edge::with_metadata(
+            EdgeKind::References,
+            EdgeMetadata::TypeRef { param_index: None },
+        ),
+    );
+    graph.add_edge(
+        place,
+        m,
+        Edge::with_metadata(
+            EdgeKind::References,
+            EdgeMetadata::Reference {
+                line: 11,
+                confidence: Confidence::Medium,
+            },
+        ),
+    );
+    graph
+}
+
+fn assert_same(a: &CodeGraph, b: &CodeGraph) {
+    assert_eq!(a.root_path(), b.root_path());
+    let nodes_a: Vec<_> = a.nodes().collect();
+    let nodes_b: Vec<_> = b.nodes().collect();
+    assert_eq!(nodes_a, nodes_b);
+    for (id, _) in a.nodes() {
+        let edges_a: Vec<_> = a.edges_from(id).collect();
+        let edges_b: Vec<_> = b.edges_from(id).collect();
+        assert_eq!(edges_a, edges_b, "edges from {:?}", id);
+    }
+}
+
+fn open(graph: &CodeGraph) -> CompactGraph {
+    CompactGraph::from_vec(compact::encode(graph)).unwrap()
+}
+
+#[test]
+fn test_round_trip_preserves_nodes_edges_and_ids() {
+    let graph = sample_graph();
+    let loaded = open(&graph).to_graph().unwrap();
+    assert_same(&graph, &loaded);
+    assert_eq!(
+        loaded.find_nodes(Path::new("/repo/src/orders.py"), Some("place")),
+        vec![NodeId::new(2)]
+    );
+}
+
+#[test]
+fn test_lazy_node_and_index_lookup() {
+    let graph = sample_graph();
+    let view = open(&graph);
+    assert_eq!(view.node_count(), 11);
+    assert_eq!(view.edge_count(), 7);
+    assert_eq!(view.root_path().unwrap(), PathBuf::from("/repo"));
+
+    let place = view.node(NodeId::new(2)).unwrap().unwrap();
+    assert_eq!(&place, graph.node(NodeId::new(2)).unwrap());
+    assert!(view.node(NodeId::new(11)).unwrap().is_none());
+
+    let file = Path::new("/repo/src/orders.py");
+    // `Order` is both a class and a database model
+    assert_eq!(
+        view.find_nodes(file, "Order").unwrap(),
+        vec![NodeId::new(3), NodeId::new(9)]
+    );
+    assert_eq!(
+        view.find_nodes(file, "place").unwrap(),
+        vec![NodeId::new(2)]
+    );
+    assert!(view.find_nodes(file, "missing").unwrap().is_empty());
+    assert!(view
+        .find_nodes(Path::new("/repo/other.py"), "place")
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_strings_are_interned() {
+    let mut graph = CodeGraph::new(PathBuf::from("/repo"));
+    let data = || NodeData::Function {
+        parameters: vec![],
+        return_type: Some("Response".to_string()),
+    };
+    let file = PathBuf::from("/repo/src/a_rather_long_module_path/handlers.py");
+    graph.add_node(Node::new(
+        NodeKind::Function,
+        "a".into(),
+        file.clone(),
+        1,
+        data(),
+    ));
+    let one = compact::encode(&graph).len();
+    for i in 0..100 {
+        graph.add_node(Node::new(
+            NodeKind::Function,
+            "a".into(),
+            file.clone(),
+            i,
+            data(),
+        ));
+    }
+    // Each extra node costs its 32-byte record, 12-byte index entry and
+    // 17 bytes of data (tag, parameter count, return type id, decorator and
+    // type-parameter counts) — never its strings
+    let per_node = (compact::encode(&graph).len() - one) / 100;
+    assert_eq!(per_node, 32 + 12 + 17);
+}
+
+#[test]
+fn test_nodes_without_fingerprints_get_them_on_load() {
+    // What a version 5 file decodes to
+    let graph = unfingerprinted_sample_graph();
+    assert!(graph.nodes().all(|(_, n)| n.fingerprint().is_none()));
+    let expected = sample_graph().fingerprints();
+
+    let loaded = open(&graph).to_graph().unwrap();
+    let fingerprints: Vec<_> = loaded
+        .nodes()
+        .map(|(_, n)| *n.fingerprint().unwrap())
+        .collect();
+    assert_eq!(fingerprints, expected);
+}
+
+#[test]
+fn test_empty_graph() {
+    let graph = CodeGraph::new(PathBuf::from("/repo"));
+    let view = open(&graph);
+    assert_eq!(view.node_count(), 0);
+    assert!(view
+        .find_nodes(Path::new("/repo/a.py"), "f")
+        .unwrap()
+        .is_empty());
+    assert_same(&graph, &view.to_graph().unwrap());
+}
+
+// ── Validation ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_checksum_mismatch_is_rejected() {
+    let mut bytes = compact::encode(&sample_graph());
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xff;
+    let err = CompactGraph::from_vec(bytes).err().unwrap();
+    assert!(err.to_string().contains("checksum mismatch"), "{}", err);
+}
+
+#[test]
+fn test_version_mismatch_is_rejected() {
+    let mut bytes = compact::encode(&sample_graph());
+    bytes[8..12].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+    let err = CompactGraph::from_vec(bytes).err().unwrap();
+    assert!(err.to_string().contains("version"), "{}", err);
+}
+
+#[test]
+fn test_truncated_and_foreign_files_are_rejected() {
+    let bytes = compact::encode(&sample_graph());
+    assert!(CompactGraph::from_vec(bytes[..bytes.len() - 10].to_vec()).is_err());
+    assert!(CompactGraph::from_vec(bytes[..20].to_vec()).is_err());
+    assert!(CompactGraph::from_vec(Vec::new()).is_err());
+    assert!(CompactGraph::from_vec(rmp_serde::to_vec(&sample_graph()).unwrap()).is_err());
+}
+
+// ── GraphCache integration ───────────────────────────────────────────────────
+
+#[test]
+fn test_graph_cache_compact_save_and_load() {
+    let dir = TempDir::new().unwrap();
+    let cache = GraphCache::with_format(dir.path(), CacheFormat::Compact);
+    let graph = sample_graph();
+    cache.save(&graph, &meta()).unwrap();
+
+    assert!(dir.path().join(".revet-cache/graph.compact").exists());
+    assert!(!dir.path().join(".revet-cache/graph.msgpack").exists());
+    let view = CompactGraph::open(&cache.graph_path()).unwrap();
+    assert_eq!(view.node_count(), 11);
+
+    let (loaded, loaded_meta) = cache.load().unwrap().unwrap();
+    assert_eq!(loaded_meta.commit_hash.as_deref(), Some("abc123"));
+    assert_same(&graph, &loaded);
+
+    // Saving again replaces the file in place
+    cache.save(&graph, &meta()).unwrap();
+    assert_same(&graph, &cache.load().unwrap().unwrap().0);
+}
+
+#[test]
+fn test_corrupt_compact_cache_is_removed_for_rebuild() {
+    let dir = TempDir::new().unwrap();
+    let cache = GraphCache::with_format(dir.path(), CacheFormat::Compact);
+    cache.save(&sample_graph(), &meta()).unwrap();
+
+    let path = cache.graph_path();
+    let mut bytes = std::fs::read(&path).unwrap();
+    let mid = bytes.len() / 2;
+    bytes[mid] ^= 0xff;
+    std::fs::write(&path, bytes).unwrap();
+
+    assert!(cache.load().is_err());
+    assert!(!path.exists());
+    // Next run sees a miss and rebuilds
+    assert!(cache.load().unwrap().is_none());
+}
+
+#[test]
+fn test_msgpack_remains_the_default() {
+    let config = RevetConfig::default();
+    assert_eq!(config.cache.format, "msgpack");
+    assert_eq!(
+        CacheFormat::from_config(&config.cache),
+        CacheFormat::Msgpack
+    );
+
+    let dir = TempDir::new().unwrap();
+    GraphCache::new(dir.path())
+        .save(&sample_graph(), &meta())
+        .unwrap();
+    assert!(dir.path().join(".revet-cache/graph.msgpack").exists());
+    // A compact reader does not pick up the msgpack file
+    let compact = GraphCache::with_format(dir.path(), CacheFormat::Compact);
+    assert!(compact.load().unwrap().is_none());
+}
+
+#[test]
+fn test_cache_config() {
+    let config: RevetConfig = toml::from_str("[cache]\nformat = \"compact\"\n").unwrap();
+    assert_eq!(
+        CacheFormat::from_config(&config.cache),
+        CacheFormat::Compact
+    );
+    assert!(config.validate().0.is_empty());
+
+    let config: RevetConfig = toml::from_str("[cache]\nformat = \"bincode\"\n").unwrap();
+    let (errors, _) = config.validate();
+    assert!(errors.iter().any(|e| e.contains("[cache] format")));
+}
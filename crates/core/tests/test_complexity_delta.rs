@@ -0,0 +1,276 @@
+//! Integration tests for complexity delta mode (`[analyzers.complexity]
+//! mode = "delta"`)
+
+mod common;
+
+use common::python_fn;
+use git2::{IndexAddOption, Repository, Signature};
+use revet_core::analyzer::complexity::delta::{DeltaBase, DeltaSummary};
+use revet_core::config::RevetConfig;
+use revet_core::{
+    AnalyzerDispatcher, DiffFileLines, DiffLineMap, Finding, GitObjects, ParserDispatcher,
+};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/complexity_delta")
+}
+
+fn fixture_config() -> RevetConfig {
+    RevetConfig::from_file(&fixture_dir().join(".revet.toml")).unwrap()
+}
+
+/// A repository with `base/` committed and `head/` in the working tree
+fn fixture_repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    let repo = Repository::init(root).unwrap();
+    fs::copy(fixture_dir().join(".revet.toml"), root.join(".revet.toml")).unwrap();
+    fs::copy(
+        fixture_dir().join("base/billing.py"),
+        root.join("billing.py"),
+    )
+    .unwrap();
+
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"].iter(), IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = Signature::now("dev", "dev@example.com").unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "base", &tree, &[])
+        .unwrap();
+
+    fs::copy(
+        fixture_dir().join("head/billing.py"),
+        root.join("billing.py"),
+    )
+    .unwrap();
+    dir
+}
+
+fn head_graph(root: &Path, files: &[&str]) -> revet_core::CodeGraph {
+    let files: Vec<PathBuf> = files.iter().map(|f| root.join(f)).collect();
+    ParserDispatcher::new()
+        .parse_files_parallel(&files, root.to_path_buf())
+        .0
+}
+
+/// (function name, message) of each finding, sorted
+fn rows(findings: &[Finding]) -> Vec<(String, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .map(|f| {
+            let name = f.message.split('`').nth(1).unwrap_or_default().to_string();
+            (name, f.message.clone())
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+// ── Fixture ──────────────────────────────────────────────────────
+
+#[test]
+fn test_fixture_reports_only_worse_and_new_functions() {
+    let dir = fixture_repo();
+    let root = dir.path().canonicalize().unwrap();
+    let config = fixture_config();
+    let graph = head_graph(&root, &["billing.py"]);
+    let dispatcher = AnalyzerDispatcher::new();
+
+    let (absolute, _) = dispatcher.run_graph_analyzers_timed(&graph, &config);
+    let absolute: Vec<_> = rows(&absolute).into_iter().map(|r| r.0).collect();
+    assert_eq!(
+        absolute,
+        vec![
+            "apply_discounts",
+            "loyalty_points",
+            "refund_total",
+            "shipping_cost"
+        ]
+    );
+
+    let base = DeltaBase::at_ref(
+        &root,
+        "HEAD",
+        &ParserDispatcher::new(),
+        &GitObjects::default(),
+    )
+    .unwrap();
+    assert_eq!(base.file_count(), 1);
+    let (findings, _, summary) = dispatcher.run_graph_analyzers_delta(&graph, &config, &base);
+    assert_eq!(
+        rows(&findings),
+        vec![
+            (
+                "loyalty_points".to_string(),
+                "Function `loyalty_points` has cyclomatic complexity of 6 (recommended: <5)"
+                    .to_string()
+            ),
+            (
+                "shipping_cost".to_string(),
+                "Function `shipping_cost` got more complex in this change: cyclomatic complexity 6 → 7 (threshold 5)"
+                    .to_string()
+            ),
+        ]
+    );
+    assert!(findings.iter().all(|f| f.id.starts_with("CMPLX-")));
+    assert_eq!(
+        summary,
+        Some(DeltaSummary {
+            reported: 2,
+            deferred: 2,
+            absolute: 4,
+        })
+    );
+}
+
+#[test]
+fn test_no_summary_when_complexity_is_off() {
+    let dir = fixture_repo();
+    let root = dir.path().canonicalize().unwrap();
+    let mut config = fixture_config();
+    config.modules.complexity = false;
+    let graph = head_graph(&root, &["billing.py"]);
+    let base = DeltaBase::at_ref(
+        &root,
+        "HEAD",
+        &ParserDispatcher::new(),
+        &GitObjects::default(),
+    )
+    .unwrap();
+    let (findings, _, summary) =
+        AnalyzerDispatcher::new().run_graph_analyzers_delta(&graph, &config, &base);
+    assert!(findings.is_empty());
+    assert_eq!(summary, None);
+}
+
+#[test]
+fn test_unknown_ref_is_an_error() {
+    let dir = fixture_repo();
+    assert!(DeltaBase::at_ref(
+        dir.path(),
+        "no-such-branch",
+        &ParserDispatcher::new(),
+        &GitObjects::default()
+    )
+    .is_err());
+}
+
+// ── Matching ─────────────────────────────────────────────────────
+
+/// Delta findings for `head` against `base`, with every line of `head`
+/// marked as changed
+fn delta(base: &str, head: &str) -> (Vec<Finding>, DeltaSummary) {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    fs::write(root.join("m.py"), head).unwrap();
+    let lines: HashSet<usize> = (1..=head.lines().count()).collect();
+    let changed: DiffLineMap =
+        HashMap::from([(PathBuf::from("m.py"), DiffFileLines::Lines(lines))]);
+    let sources = HashMap::from([(PathBuf::from("m.py"), base.to_string())]);
+    let base = DeltaBase::new(&root, changed, sources, &ParserDispatcher::new());
+
+    let mut config = RevetConfig::default();
+    config.modules.complexity = true;
+    config.modules.complexity_threshold = 5;
+    let graph = head_graph(&root, &["m.py"]);
+    let (findings, _, summary) =
+        AnalyzerDispatcher::new().run_graph_analyzers_delta(&graph, &config, &base);
+    (findings, summary.unwrap())
+}
+
+#[test]
+fn test_moved_function_matches_its_base_version() {
+    let legacy = python_fn("legacy", 6);
+    let base = format!("{}{}", python_fn("helper", 0), legacy);
+    let head = format!("{}{}", legacy, python_fn("helper", 1));
+    let (findings, summary) = delta(&base, &head);
+    assert!(findings.is_empty(), "{:?}", findings);
+    assert_eq!(summary.deferred, 1);
+}
+
+#[test]
+fn test_threshold_reached_by_the_change() {
+    let (findings, _) = delta(&python_fn("f", 3), &python_fn("f", 4));
+    assert_eq!(
+        findings[0].message,
+        "Function `f` got more complex in this change: cyclomatic complexity 4 → 5 (threshold 5)"
+    );
+
+    // Worse, but still under the threshold
+    let (findings, summary) = delta(&python_fn("f", 1), &python_fn("f", 3));
+    assert!(findings.is_empty());
+    assert_eq!(summary, DeltaSummary::default());
+
+    // Better, still over it
+    let (findings, summary) = delta(&python_fn("f", 8), &python_fn("f", 6));
+    assert!(findings.is_empty());
+    assert_eq!(summary.deferred, 1);
+}
+
+#[test]
+fn test_changed_parameters_match_by_unique_name() {
+    let (findings, _) = delta(
+        &python_fn("f", 6),
+        &python_fn("f", 6).replace("(x)", "(x, y)"),
+    );
+    assert!(findings.is_empty(), "{:?}", findings);
+}
+
+#[test]
+fn test_ambiguous_functions_are_new() {
+    // Two same-named functions in base, one in head: no counterpart, so the
+    // absolute threshold applies
+    let base = format!("{}{}", python_fn("f", 6), python_fn("f", 6));
+    let (findings, summary) = delta(&base, &python_fn("f", 6));
+    assert_eq!(
+        findings[0].message,
+        "Function `f` has cyclomatic complexity of 7 (recommended: <5)"
+    );
+    assert_eq!(summary.reported, 1);
+
+    // As many in both: paired in file order
+    let (findings, _) = delta(&base, &base);
+    assert!(findings.is_empty(), "{:?}", findings);
+}
+
+#[test]
+fn test_added_file_uses_absolute_thresholds() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    fs::write(root.join("new.py"), python_fn("f", 6)).unwrap();
+    let changed: DiffLineMap = HashMap::from([(PathBuf::from("new.py"), DiffFileLines::AllNew)]);
+    let base = DeltaBase::new(&root, changed, HashMap::new(), &ParserDispatcher::new());
+
+    let mut config = RevetConfig::default();
+    config.modules.complexity = true;
+    config.modules.complexity_threshold = 5;
+    let graph = head_graph(&root, &["new.py"]);
+    let (findings, _, summary) =
+        AnalyzerDispatcher::new().run_graph_analyzers_delta(&graph, &config, &base);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(summary.unwrap().deferred, 0);
+}
+
+// ── Config ───────────────────────────────────────────────────────
+
+#[test]
+fn test_mode_validation() {
+    assert_eq!(RevetConfig::default().analyzers.complexity.mode, "absolute");
+    assert_eq!(fixture_config().analyzers.complexity.mode, "delta");
+
+    let config: RevetConfig =
+        toml::from_str("[analyzers.complexity]\nmode = \"relative\"\n").unwrap();
+    let (errors, _) = config.validate();
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("[analyzers.complexity] mode = \"relative\"")));
+    let (errors, _) = fixture_config().validate();
+    assert!(errors.is_empty(), "{:?}", errors);
+}
@@ -0,0 +1,204 @@
+//! Tests for the concurrency analyzer: the `concurrency` fixture, with a
+//! guarded and an unguarded variant of each pattern per language
+
+use revet_core::analyzer::concurrency::{find_races, ConcurrencyAnalyzer};
+use revet_core::analyzer::GraphAnalyzer;
+use revet_core::config::RevetConfig;
+use revet_core::discovery::discover_files;
+use revet_core::finding::Confidence;
+use revet_core::graph::CodeGraph;
+use revet_core::{ParserDispatcher, Severity};
+use std::path::{Path, PathBuf};
+
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures/concurrency")
+        .canonicalize()
+        .unwrap()
+}
+
+fn config() -> RevetConfig {
+    RevetConfig::from_file(&root().join(".revet.toml")).unwrap()
+}
+
+fn graph_of(root: &Path, config: &RevetConfig) -> CodeGraph {
+    let dispatcher = ParserDispatcher::new_with_config(config);
+    let files = discover_files(root, &dispatcher.supported_extensions(), &[]).unwrap();
+    let (graph, errors) = dispatcher.parse_files_parallel(&files, root.to_path_buf());
+    assert!(errors.is_empty(), "{:?}", errors);
+    graph
+}
+
+/// `(rule, path:line, message)` of every finding
+fn findings(config: &RevetConfig) -> Vec<(String, String, String)> {
+    let graph = graph_of(&root(), config);
+    ConcurrencyAnalyzer::new()
+        .analyze_graph(&graph, config)
+        .into_iter()
+        .map(|f| {
+            let file = f.file.strip_prefix(root()).unwrap().display();
+            (f.rule.unwrap(), format!("{}:{}", file, f.line), f.message)
+        })
+        .collect()
+}
+
+#[test]
+fn test_unguarded_mutations_reached_concurrently() {
+    let found = findings(&config());
+    let located: Vec<(&str, &str)> = found
+        .iter()
+        .map(|(rule, at, _)| (rule.as_str(), at.as_str()))
+        .collect();
+    assert_eq!(
+        located,
+        [
+            // `list_items` only reads; `mark_seen` holds `_lock`
+            ("shared_state", "python/api.py:13"),
+            // `record_locked` holds `LOCK`, `lookup` only reads,
+            // `local_only` shadows `CACHE`, `register_safe` acquires a lock
+            // and `sequential` runs on no thread
+            ("shared_state", "python/workers.py:11"),
+            ("shared_state", "python/workers.py:21"),
+            ("shared_state", "python/workers.py:43"),
+            // `GET` only reads
+            ("handler_state", "web/app/api/items/route.ts:5"),
+            // `/logout` runs exclusively, `/region` reads, `/local` shadows
+            ("handler_state", "web/server.ts:11"),
+            ("handler_state", "web/server.ts:18"),
+            // `LIMIT` is immutable, `REQUESTS` atomic, `Clone` safe
+            ("static_mut", "rust/lib.rs:3"),
+            ("unsafe_send_sync", "rust/lib.rs:9"),
+            ("unsafe_send_sync", "rust/lib.rs:10"),
+        ]
+    );
+    // Test code mutating shared state from a thread is left out
+    assert!(found.iter().all(|(_, at, _)| !at.starts_with("tests/")));
+}
+
+#[test]
+fn test_messages_name_the_state_and_the_entry_point() {
+    let found = findings(&config());
+    let message = |at: &str| {
+        found
+            .iter()
+            .find(|(_, location, _)| location == at)
+            .map(|(_, _, m)| m.as_str())
+            .unwrap()
+    };
+    assert_eq!(
+        message("python/workers.py:11"),
+        "`CACHE` is mutated without a lock in `record`, which runs concurrently from `threading.Thread(target=worker)`"
+    );
+    assert_eq!(
+        message("python/workers.py:43"),
+        "`Registry.items` is mutated without a lock in `Registry.register`, which runs concurrently from `executor.submit(registry.register)`"
+    );
+    assert_eq!(
+        message("python/api.py:13"),
+        "`ITEMS` is mutated without a lock in `create_item`, which runs concurrently from `POST /items`"
+    );
+    assert_eq!(
+        message("web/server.ts:11"),
+        "Module-level `sessions` is written in `app.post(...)`, which runs for concurrent requests from `POST /login`"
+    );
+    assert_eq!(
+        message("web/app/api/items/route.ts:5"),
+        "Module-level `items` is written in `POST`, which runs for concurrent requests from `export function POST`"
+    );
+    assert_eq!(
+        message("rust/lib.rs:9"),
+        "`unsafe impl Send for Handle` asserts thread safety the compiler can't check"
+    );
+
+    let config = config();
+    let graph = graph_of(&root(), &config);
+    let races = find_races(&graph, &config);
+    let count = races.iter().find(|r| r.function == "count").unwrap();
+    assert_eq!(count.mutation.var, "processed");
+    assert_eq!(count.mutation.declared, 7);
+    assert_eq!(
+        (count.entry_file.file_name().unwrap(), count.entry_line),
+        ("workers.py".as_ref(), 61)
+    );
+}
+
+#[test]
+fn test_confidence_and_severity() {
+    let mut config = config();
+    config
+        .analyzers
+        .concurrency
+        .severity
+        .insert("shared_state".to_string(), "error".to_string());
+    let graph = graph_of(&root(), &config);
+    for finding in ConcurrencyAnalyzer::new().analyze_graph(&graph, &config) {
+        let (confidence, severity) = match finding.rule.as_deref() {
+            Some("shared_state") => (Confidence::Medium, Severity::Error),
+            Some("handler_state") => (Confidence::Medium, Severity::Warning),
+            _ => (Confidence::High, Severity::Warning),
+        };
+        assert_eq!(finding.confidence, confidence, "{}", finding.message);
+        assert_eq!(finding.severity, severity, "{}", finding.message);
+    }
+}
+
+#[test]
+fn test_entry_points_and_handlers_are_configurable() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("jobs.py"),
+        "import gevent\n\nSEEN = set()\n\n\ndef crawl(url):\n    SEEN.add(url)\n\n\ndef start(urls):\n    for url in urls:\n        gevent.spawn(crawl, url)\n",
+    )
+    .unwrap();
+    let mut config = RevetConfig::default();
+    config.modules.concurrency = true;
+    let graph = graph_of(dir.path(), &config);
+    assert!(find_races(&graph, &config).is_empty());
+    config.analyzers.concurrency.entry_points = vec!["spawn".to_string()];
+    let races = find_races(&graph, &config);
+    assert_eq!(races.len(), 1);
+    assert_eq!(races[0].entry, "gevent.spawn(crawl)");
+    assert_eq!(races[0].mutation.line, 7);
+
+    // Without handlers, routes run nothing concurrently
+    let mut config = self::config();
+    config.analyzers.concurrency.handlers = false;
+    let found = findings(&config);
+    assert!(found.iter().all(|(rule, _, _)| rule != "handler_state"));
+    assert!(!found.iter().any(|(_, at, _)| at == "python/api.py:13"));
+    assert!(found.iter().any(|(_, at, _)| at == "python/workers.py:11"));
+}
+
+#[test]
+fn test_rust_items_in_unsaved_content() {
+    let config = config();
+    let graph = graph_of(&root(), &config);
+    let file = root().join("rust/lib.rs");
+    let content = "static mut READY: bool = false;\nstatic mut COUNTER: u32 = 0;\n";
+    let found = ConcurrencyAnalyzer::new().analyze_file(&graph, &file, content, &config);
+    let lines: Vec<usize> = found.iter().map(|f| f.line).collect();
+    assert_eq!(lines, [1, 2]);
+    assert_eq!(
+        found[0].message,
+        "`static mut READY` can be read and written from any thread without synchronization"
+    );
+}
+
+#[test]
+fn test_config_validation() {
+    let mut config = config();
+    assert!(config.validate().0.is_empty());
+    config.analyzers.concurrency.max_depth = 0;
+    config
+        .analyzers
+        .concurrency
+        .severity
+        .insert("races".to_string(), "error".to_string());
+    let errors = config.validate().0.join("\n");
+    assert!(
+        errors.contains("max_depth must be at least 1"),
+        "{}",
+        errors
+    );
+    assert!(errors.contains("unknown sub-rule \"races\""), "{}", errors);
+}
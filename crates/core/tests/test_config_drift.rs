@@ -0,0 +1,91 @@
+//! Tests for configuration drift: which differences between two resolved
+//! configs are weakening or strengthening, and the config lines they point at
+
+use revet_core::analyzer::config_drift::{compare, ConfigSide};
+use revet_core::config::{ConfigFile, RevetConfig};
+use revet_core::finding::Severity;
+use std::path::{Path, PathBuf};
+
+const BASE: &str = r#"[general]
+fail_on = "error"
+
+[modules]
+security = true
+complexity_threshold = 10
+
+[ignore]
+paths = ["vendor/**"]
+findings = []
+"#;
+
+fn side(text: &str, baseline: Option<usize>) -> ConfigSide {
+    let path = PathBuf::from("/repo/.revet.toml");
+    ConfigSide {
+        config: RevetConfig::parse(text, &path).unwrap(),
+        file: Some(ConfigFile::new(path, text.to_string())),
+        baseline,
+    }
+}
+
+/// `(message key, severity, line)` of each finding for `base` → `head`
+fn drift(base: &ConfigSide, head: &ConfigSide) -> Vec<(String, Severity, usize)> {
+    compare(base, head, Path::new("/repo/.revet/baseline.json"))
+        .into_iter()
+        .map(|f| (f.message_ref.unwrap().key().to_string(), f.severity, f.line))
+        .collect()
+}
+
+fn change(key: &str, severity: Severity, line: usize) -> (String, Severity, usize) {
+    (key.to_string(), severity, line)
+}
+
+#[test]
+fn test_identical_configs_report_nothing() {
+    assert!(drift(&side(BASE, Some(3)), &side(BASE, Some(3))).is_empty());
+}
+
+#[test]
+fn test_weakening_changes_are_warnings() {
+    let head = BASE
+        .replace("security = true", "security = false")
+        .replace("fail_on = \"error\"", "fail_on = \"never\"")
+        .replace("findings = []", "findings = [\"SEC-001\"]");
+    assert_eq!(
+        drift(&side(BASE, Some(3)), &side(&head, Some(5))),
+        vec![
+            change("config-drift-module-disabled", Severity::Warning, 5),
+            change("config-drift-finding-ignored", Severity::Warning, 10),
+            change("config-drift-fail-on-loosened", Severity::Warning, 2),
+            change("config-drift-baseline-grown", Severity::Warning, 1),
+        ]
+    );
+}
+
+#[test]
+fn test_strengthening_changes_are_info() {
+    let head = BASE
+        .replace("complexity_threshold = 10", "complexity_threshold = 8")
+        .replace("paths = [\"vendor/**\"]", "paths = []");
+    assert_eq!(
+        drift(&side(BASE, Some(3)), &side(&head, Some(1))),
+        vec![
+            change("config-drift-threshold-lowered", Severity::Info, 6),
+            change("config-drift-path-included", Severity::Info, 9),
+            change("config-drift-baseline-shrunk", Severity::Info, 1),
+        ]
+    );
+}
+
+#[test]
+fn test_line_of_follows_key_spans() {
+    let file = ConfigFile::new(PathBuf::from(".revet.toml"), BASE.to_string());
+    assert_eq!(file.line_of(&["general", "fail_on"]), Some(2));
+    assert_eq!(file.line_of(&["modules"]), Some(4));
+    assert_eq!(file.line_of(&["modules", "complexity_threshold"]), Some(6));
+    assert_eq!(file.line_of(&["ignore", "paths"]), Some(9));
+    assert_eq!(
+        file.line_of_entry(&["ignore", "paths"], "vendor/**"),
+        Some(9)
+    );
+    assert_eq!(file.line_of(&["modules", "missing"]), None);
+}
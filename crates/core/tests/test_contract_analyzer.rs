@@ -0,0 +1,449 @@
+//! Integration tests for ContractAnalyzer
+
+mod common;
+
+use revet_core::analyzer::contract::{template, ContractAnalyzer};
+use revet_core::analyzer::{Analyzer, AnalyzerDispatcher};
+use revet_core::config::{ContractConfig, GeneratedCodeConfig, RevetConfig};
+use revet_core::finding::{Confidence, Finding, Severity};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const FIXTURE: &str = "contract";
+const EXTENSIONS: &[&str] = &[".py", ".ts"];
+
+/// (repo-relative file, line, sub-rule) for each CONTRACT finding, sorted
+fn summary(findings: &[Finding], root: &Path) -> Vec<(String, usize, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("CONTRACT"))
+        .map(|f| {
+            let file = f.file.strip_prefix(root).unwrap_or(&f.file);
+            (
+                file.to_string_lossy().replace('\\', "/"),
+                f.line,
+                f.rule.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+fn row(file: &str, line: usize, rule: &str) -> (String, usize, String) {
+    (file.to_string(), line, format!("contract:{}", rule))
+}
+
+/// Write `files` into a temporary repository and analyze them
+fn analyze_with(
+    analyzer: ContractAnalyzer,
+    files: &[(&str, &str)],
+) -> (TempDir, Vec<(String, usize, String)>, Vec<Finding>) {
+    let tmp = TempDir::new().unwrap();
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for (name, content) in files {
+        let path = tmp.path().join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+        paths.push(path);
+    }
+    let findings = analyzer.analyze_files(&paths, tmp.path());
+    let rows: Vec<_> = findings
+        .iter()
+        .map(|f| {
+            let file = f.file.strip_prefix(tmp.path()).unwrap_or(&f.file);
+            (
+                file.to_string_lossy().replace('\\', "/"),
+                f.line,
+                f.rule.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    (tmp, rows, findings)
+}
+
+fn analyze(files: &[(&str, &str)]) -> Vec<(String, usize, String)> {
+    analyze_with(ContractAnalyzer::new(), files).1
+}
+
+fn at(file: &str, line: usize, rule: &str) -> (String, usize, String) {
+    (file.to_string(), line, rule.to_string())
+}
+
+const USERS_API: &str = r#"from fastapi import APIRouter, FastAPI
+
+app = FastAPI()
+router = APIRouter(prefix="/users")
+
+
+@router.get("/{user_id}")
+def get_user(user_id: int):
+    return {}
+
+
+app.include_router(router)
+"#;
+
+#[test]
+fn test_fixture_findings() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    assert_eq!(
+        summary(&findings, &root),
+        vec![
+            row("app/routers/orders.py", 24, "uncalled_endpoint"),
+            row("src/api/users.ts", 18, "field_mismatch"),
+            row("src/api/users.ts", 37, "unmatched_call"),
+        ]
+    );
+}
+
+#[test]
+fn test_fixture_severities_and_messages() {
+    let (_, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let find = |rule: &str| {
+        findings
+            .iter()
+            .find(|f| f.rule.as_deref() == Some(rule))
+            .unwrap_or_else(|| panic!("no {} finding", rule))
+    };
+
+    let unmatched = find("contract:unmatched_call");
+    assert_eq!(unmatched.severity, Severity::Error);
+    assert_eq!(unmatched.confidence, Confidence::High);
+    assert!(
+        unmatched.message.contains("PUT /api/users/${}/avatar"),
+        "{}",
+        unmatched.message
+    );
+
+    let uncalled = find("contract:uncalled_endpoint");
+    assert_eq!(uncalled.severity, Severity::Info);
+    assert!(
+        uncalled.message.contains("DELETE /orders/{order_id}")
+            && uncalled.message.contains("cancel_order"),
+        "{}",
+        uncalled.message
+    );
+
+    let mismatch = find("contract:field_mismatch");
+    assert_eq!(mismatch.severity, Severity::Warning);
+    assert_eq!(mismatch.confidence, Confidence::Medium);
+    assert!(
+        mismatch.message.contains("UserCreate")
+            && mismatch.message.contains("emailAddress")
+            && mismatch.message.contains("email)"),
+        "{}",
+        mismatch.message
+    );
+    assert!(mismatch
+        .suggestion
+        .as_deref()
+        .is_some_and(|s| s.contains("app/models.py:10")));
+}
+
+#[test]
+fn test_parameter_segments_are_normalised() {
+    assert_eq!(template("/users/{user_id}"), vec!["users", "{}"]);
+    assert_eq!(template("/users/:id"), vec!["users", "{}"]);
+    assert_eq!(template("/users/<int:user_id>/"), vec!["users", "{}"]);
+    assert_eq!(template("/users/${}?expand=1"), vec!["users", "{}"]);
+
+    let rows = analyze(&[
+        ("api/users.py", USERS_API),
+        (
+            "web/a.ts",
+            "fetch(`/users/${id}`);\nfetch('/users/' + id);\nfetch(\"/users/42\");\n",
+        ),
+    ]);
+    assert!(rows.is_empty(), "{:?}", rows);
+}
+
+#[test]
+fn test_typo_suggests_the_closest_endpoint() {
+    let (_, rows, findings) = analyze_with(
+        ContractAnalyzer::new(),
+        &[
+            ("api/users.py", USERS_API),
+            ("web/a.ts", "fetch(`/user/${id}`);\n"),
+        ],
+    );
+    assert_eq!(
+        rows,
+        vec![
+            at("web/a.ts", 1, "unmatched_call"),
+            at("api/users.py", 7, "uncalled_endpoint"),
+        ]
+    );
+    let suggestion = findings[0].suggestion.as_deref().unwrap();
+    assert!(
+        suggestion.contains("GET /users/{user_id}") && suggestion.contains("api/users.py:7"),
+        "{}",
+        suggestion
+    );
+}
+
+#[test]
+fn test_wrong_method_names_the_served_ones() {
+    let (_, rows, findings) = analyze_with(
+        ContractAnalyzer::new(),
+        &[
+            ("api/users.py", USERS_API),
+            (
+                "web/a.ts",
+                "fetch(`/users/${id}`);\nfetch(`/users/${id}`, { method: \"DELETE\" });\n",
+            ),
+        ],
+    );
+    assert_eq!(rows, vec![at("web/a.ts", 2, "unmatched_call")]);
+    assert!(
+        findings[0].message.contains("DELETE /users/${}")
+            && findings[0].message.contains("served for GET"),
+        "{}",
+        findings[0].message
+    );
+}
+
+#[test]
+fn test_flask_blueprints_and_express_routers_are_mounted() {
+    let rows = analyze(&[
+        (
+            "api/app.py",
+            r#"from flask import Flask
+
+from api.orders import bp
+
+app = Flask(__name__)
+app.register_blueprint(bp, url_prefix="/v1/orders")
+"#,
+        ),
+        (
+            "api/orders.py",
+            r#"from flask import Blueprint
+
+bp = Blueprint("orders", __name__, url_prefix="/orders")
+
+
+@bp.route("/<int:order_id>", methods=["GET", "PUT"])
+def order(order_id):
+    return {}
+"#,
+        ),
+        (
+            "server/app.js",
+            r#"const express = require("express");
+const users = require("./routes/users");
+
+const app = express();
+app.use("/users", users);
+"#,
+        ),
+        (
+            "server/routes/users.js",
+            r#"const express = require("express");
+const router = express.Router();
+
+router.get("/:id", (req, res) => res.json({}));
+
+module.exports = router;
+"#,
+        ),
+        (
+            "web/a.ts",
+            r#"fetch(`/v1/orders/${id}`);
+fetch(`/v1/orders/${id}`, { method: "PUT", body: JSON.stringify(order) });
+fetch(`/users/${id}`);
+"#,
+        ),
+    ]);
+    assert!(rows.is_empty(), "{:?}", rows);
+}
+
+#[test]
+fn test_axios_instances_and_origins() {
+    let rows = analyze(&[
+        ("api/users.py", USERS_API),
+        (
+            "web/a.ts",
+            r#"import axios from "axios";
+
+const API_URL = import.meta.env.VITE_API_URL;
+const client = axios.create({ baseURL: "/users" });
+
+axios.get(`${API_URL}/users/${id}`);
+client.get(`/${id}`);
+axios({ url: "/users/1", method: "get" });
+fetch("https://api.github.com/users/octocat");
+fetch("/locales/en.json");
+"#,
+        ),
+    ]);
+    assert!(rows.is_empty(), "{:?}", rows);
+}
+
+#[test]
+fn test_dynamic_urls_are_unverifiable() {
+    let (_, rows, findings) = analyze_with(
+        ContractAnalyzer::new(),
+        &[
+            ("api/users.py", USERS_API),
+            (
+                "web/a.ts",
+                "fetch(`/users/${id}`);\nfetch(buildUrl(\"users\", id));\nfetch(`${path}`);\n",
+            ),
+        ],
+    );
+    assert_eq!(
+        rows,
+        vec![
+            at("web/a.ts", 2, "dynamic_url"),
+            at("web/a.ts", 3, "dynamic_url"),
+        ]
+    );
+    assert_eq!(findings[0].confidence, Confidence::Low);
+    assert_eq!(findings[0].severity, Severity::Info);
+}
+
+#[test]
+fn test_body_fields_against_models() {
+    let models = r#"from fastapi import APIRouter
+from pydantic import BaseModel, ConfigDict, Field
+from pydantic.alias_generators import to_camel
+
+router = APIRouter()
+
+
+class Base(BaseModel):
+    model_config = ConfigDict(alias_generator=to_camel)
+
+    user_id: int
+
+
+class Move(Base):
+    target: str = Field(alias="to")
+    reason: str | None = None
+
+
+@router.post("/moves")
+def move(body: Move):
+    return {}
+"#;
+    let rows = analyze(&[
+        ("api/moves.py", models),
+        (
+            "web/ok.ts",
+            r#"interface Target { to: string }
+interface MoveRequest extends Target { userId: number; reason?: string }
+const request: MoveRequest = load();
+fetch("/moves", { method: "POST", body: JSON.stringify(request) });
+fetch("/moves", { method: "POST", body: JSON.stringify({ userId, to, ...rest }) });
+"#,
+        ),
+        (
+            "web/bad.ts",
+            "fetch(\"/moves\", { method: \"POST\", body: JSON.stringify({ user_id: 1, to: \"b\" }) });\n",
+        ),
+    ]);
+    assert_eq!(rows, vec![at("web/bad.ts", 1, "field_mismatch")]);
+}
+
+#[test]
+fn test_generated_client_calls_follow_the_spec() {
+    let spec = r#"openapi: 3.0.0
+paths:
+  /users/{user_id}:
+    get:
+      operationId: get_user
+  /users/{user_id}/avatar:
+    put:
+      operationId: upload_avatar
+"#;
+    let analyzer = ContractAnalyzer::new().with_generated(&[GeneratedCodeConfig {
+        spec: "openapi.yaml".to_string(),
+        output_dir: "web/generated".to_string(),
+        command: None,
+        hash_file: ".revet/gen-hashes.toml".to_string(),
+        allow_regenerate: false,
+    }]);
+    let (_, rows, _) = analyze_with(
+        analyzer,
+        &[
+            ("openapi.yaml", spec),
+            ("api/users.py", USERS_API),
+            (
+                "web/generated/services.ts",
+                "export class UsersService { static getUser(id: number) { return fetch(`/nope/${id}`); } }\n",
+            ),
+            (
+                "web/a.ts",
+                r#"import { UsersService } from "./generated/services";
+
+UsersService.getUser(1);
+UsersService.uploadAvatar(1, blob);
+"#,
+            ),
+        ],
+    );
+    assert_eq!(rows, vec![at("web/a.ts", 4, "unmatched_call")]);
+}
+
+#[test]
+fn test_nothing_without_endpoints_or_calls() {
+    assert!(analyze(&[("web/a.ts", "fetch(\"/users/1\");\n")]).is_empty());
+
+    let rows = analyze(&[("api/users.py", USERS_API)]);
+    assert!(rows.is_empty(), "{:?}", rows);
+}
+
+#[test]
+fn test_configuration() {
+    let config: ContractConfig = toml::from_str(
+        r#"
+base_paths = ["/api"]
+
+[severity]
+unmatched_call = "warning"
+"#,
+    )
+    .unwrap();
+    let (_, rows, findings) = analyze_with(
+        ContractAnalyzer::with_config(&config),
+        &[
+            ("api/users.py", USERS_API),
+            (
+                "web/a.ts",
+                "fetch(`/api/users/${id}`);\nfetch(\"/api/teams\");\n",
+            ),
+        ],
+    );
+    assert_eq!(rows, vec![at("web/a.ts", 2, "unmatched_call")]);
+    assert_eq!(findings[0].severity, Severity::Warning);
+
+    let mut config = RevetConfig::default();
+    config.analyzers.contract.severity =
+        [("unmatched_call".to_string(), "fatal".to_string())].into();
+    config.analyzers.contract.base_paths = vec!["api".to_string()];
+    let (errors, _) = config.validate();
+    assert!(
+        errors
+            .iter()
+            .any(|e| e.contains("[analyzers.contract.severity]")),
+        "{:?}",
+        errors
+    );
+    assert!(
+        errors.iter().any(|e| e.contains("base_paths")),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_module_off_by_default() {
+    let config = RevetConfig::default();
+    assert!(!ContractAnalyzer::new().is_enabled(&config));
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config);
+    let root = common::fixture_root(FIXTURE);
+    let files = common::discover(&root, EXTENSIONS);
+    let findings = dispatcher.run_all(&files, &root, &config);
+    assert!(summary(&findings, &root).is_empty());
+}
@@ -0,0 +1,495 @@
+//! Integration tests for CopyAnalyzer
+
+mod common;
+
+use revet_core::analyzer::copy::CopyAnalyzer;
+use revet_core::analyzer::{Analyzer, AnalyzerDispatcher};
+use revet_core::config::{CopyConfig, RevetConfig};
+use revet_core::finding::{Confidence, Finding, FixKind, Severity};
+use std::path::Path;
+use tempfile::TempDir;
+
+const FIXTURE: &str = "copy";
+const EXTENSIONS: &[&str] = &[".py", ".ts", ".tsx", ".md", ".html"];
+
+/// (repo-relative file, line, sub-rule) for each COPY finding, sorted
+fn summary(findings: &[Finding], root: &Path) -> Vec<(String, usize, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("COPY"))
+        .map(|f| {
+            let file = f.file.strip_prefix(root).unwrap_or(&f.file);
+            (
+                file.to_string_lossy().replace('\\', "/"),
+                f.line,
+                f.rule.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+fn row(file: &str, line: usize, rule: &str) -> (String, usize, String) {
+    (file.to_string(), line, format!("copy:{}", rule))
+}
+
+fn banned(toml: &str) -> CopyConfig {
+    toml::from_str(toml).unwrap()
+}
+
+/// Findings for `content` as `file` in `dir`, with notices checked against 2026
+fn analyze_in(dir: &Path, config: &CopyConfig, file: &str, content: &str) -> Vec<Finding> {
+    CopyAnalyzer::with_config(config)
+        .with_year(2026)
+        .analyze_source(&dir.join(file), content, dir)
+}
+
+fn analyze(config: &CopyConfig, file: &str, content: &str) -> Vec<Finding> {
+    let tmp = TempDir::new().unwrap();
+    analyze_in(tmp.path(), config, file, content)
+}
+
+/// (line, sub-rule) pairs
+fn lines(findings: &[Finding]) -> Vec<(usize, String)> {
+    findings
+        .iter()
+        .map(|f| (f.line, f.rule.clone().unwrap_or_default()))
+        .collect()
+}
+
+fn at(line: usize, rule: &str) -> (usize, String) {
+    (line, rule.to_string())
+}
+
+// ── Fixture ──────────────────────────────────────────────────────
+
+#[test]
+fn test_fixture() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    assert_eq!(
+        summary(&findings, &root),
+        vec![
+            row("app/about.py", 3, "copyright_year"),
+            row("app/about.py", 8, "banned_term"),
+            row("docs/guide.md", 3, "banned_term"),
+            row("docs/guide.md", 5, "version_reference"),
+            row("public/index.html", 5, "banned_term"),
+            row("public/index.html", 5, "copyright_year"),
+            row("web/Footer.tsx", 7, "version_reference"),
+            row("web/Footer.tsx", 11, "copyright_year"),
+            row("web/Footer.tsx", 12, "banned_term"),
+            row("web/Footer.tsx", 13, "banned_term"),
+            row("web/Footer.tsx", 13, "banned_term"),
+        ]
+    );
+}
+
+#[test]
+fn test_fixture_messages_severities_and_confidence() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let at = |file: &str, line: usize| {
+        findings
+            .iter()
+            .find(|f| f.file == root.join(file) && f.line == line)
+            .unwrap()
+    };
+
+    let version = at("web/Footer.tsx", 7);
+    assert_eq!(
+        version.message,
+        "Copy mentions version 3.2, 2 major version(s) behind 5.1.0"
+    );
+    assert_eq!(version.severity, Severity::Info);
+    assert_eq!(version.confidence, Confidence::Medium);
+    assert_eq!((version.column, version.end_column), (Some(26), Some(37)));
+
+    let notice = at("web/Footer.tsx", 11);
+    assert!(notice
+        .message
+        .starts_with("Copyright notice ends in 2019, but it is "));
+    assert_eq!(notice.severity, Severity::Warning);
+
+    let legacy = at("app/about.py", 8);
+    assert_eq!(legacy.message, "`LegacyName` is a retired name");
+    assert_eq!(legacy.severity, Severity::Info);
+    assert_eq!(
+        legacy.suggestion.as_deref(),
+        Some("Reword the text without `LegacyName`")
+    );
+    assert!(legacy.fix_kind.is_none());
+
+    let brand = at("docs/guide.md", 3);
+    assert_eq!(brand.severity, Severity::Warning);
+    assert_eq!(brand.suggestion.as_deref(), Some("Say `NewBrand` instead"));
+}
+
+#[test]
+fn test_fixture_changelog_is_not_flagged() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    assert!(findings.iter().all(|f| f.file != root.join("CHANGELOG.md")));
+
+    // Without the allowlist it is full of stale copy
+    let content = std::fs::read_to_string(root.join("CHANGELOG.md")).unwrap();
+    let config =
+        banned("allow_paths = []\nbanned = [{ term = \"OldBrand\" }, { term = \"LegacyName\" }]\n");
+    let findings = CopyAnalyzer::with_config(&config).analyze_source(
+        &root.join("CHANGELOG.md"),
+        &content,
+        &root,
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![
+            at(5, "banned_term"),
+            at(5, "banned_term"),
+            at(9, "version_reference"),
+            at(10, "copyright_year"),
+        ]
+    );
+}
+
+#[test]
+fn test_fixture_fix_applies() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let tmp = TempDir::new().unwrap();
+    let copy = tmp.path().join("Footer.tsx");
+    std::fs::copy(root.join("web/Footer.tsx"), &copy).unwrap();
+    let fixable: Vec<Finding> = findings
+        .into_iter()
+        .filter(|f| f.file == root.join("web/Footer.tsx"))
+        .map(|f| Finding {
+            file: copy.clone(),
+            ..f
+        })
+        .collect();
+
+    let log = TempDir::new().unwrap();
+    let report =
+        revet_core::fixer::apply_fixes(&fixable, &mut common::journal(log.path())).unwrap();
+    assert_eq!(report.applied, 2);
+    let content = std::fs::read_to_string(&copy).unwrap();
+    assert!(!content.contains("© 2019"));
+    assert!(content.contains("<span>Welcome to NewBrand</span>"));
+    // Code and the line with two mentions are left alone
+    assert!(content.contains("import { OldBrand } from \"./brand\";"));
+    assert!(content.contains("new OldBrand.Client()"));
+    assert!(content.contains("\"OldBrand is ready. Thanks for using OldBrand!\""));
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let analyzer = CopyAnalyzer::new();
+    assert!(!analyzer.is_enabled(&RevetConfig::default()));
+    assert!(analyzer.accepts(Path::new("README.md")));
+    assert!(analyzer.accepts(Path::new("index.HTML")));
+    assert!(!analyzer.accepts(Path::new("notes.txt")));
+}
+
+// ── Copyright years ──────────────────────────────────────────────
+
+#[test]
+fn test_copyright_notices() {
+    let content = r#"a = "© 2019 Acme"
+b = "(c) 2020 Acme"
+c = "Copyright 2018–2025 Acme"
+d = "COPYRIGHT © 2010 to 2024"
+e = "© 2026 Acme"
+f = "Copyright 2019-2026 Acme"
+g = "Released in 2019"
+h = "© 1899"
+"#;
+    let findings = analyze(&CopyConfig::default(), "notices.py", content);
+    assert_eq!(
+        lines(&findings),
+        vec![
+            at(1, "copyright_year"),
+            at(2, "copyright_year"),
+            at(3, "copyright_year"),
+            at(4, "copyright_year"),
+        ]
+    );
+    let replacements: Vec<_> = findings
+        .iter()
+        .map(|f| match &f.fix_kind {
+            Some(FixKind::ReplacePattern { replace, .. }) => replace.clone(),
+            other => panic!("{:?}", other),
+        })
+        .collect();
+    assert_eq!(
+        replacements,
+        vec![
+            "© 2026",
+            "(c) 2026",
+            "Copyright 2018–2026",
+            "COPYRIGHT © 2010 to 2026",
+        ]
+    );
+}
+
+#[test]
+fn test_copyright_in_comments_and_names_is_ignored() {
+    let content = "// © 2019 Acme\nconst copyright2019 = 1;\nconst label = `© ${year}`;\n";
+    assert!(analyze(&CopyConfig::default(), "a.ts", content).is_empty());
+}
+
+#[test]
+fn test_copyright_year_outside_a_repository_is_this_year() {
+    let tmp = TempDir::new().unwrap();
+    let findings = CopyAnalyzer::new().analyze_source(
+        &tmp.path().join("a.py"),
+        "a = \"© 1999 Acme\"\n",
+        tmp.path(),
+    );
+    assert_eq!(findings.len(), 1);
+    let year = &revet_core::baseline::today()[..4];
+    assert!(
+        findings[0].message.ends_with(year),
+        "{}",
+        findings[0].message
+    );
+}
+
+// ── Version references ───────────────────────────────────────────
+
+#[test]
+fn test_version_references_against_the_nearest_manifest() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(tmp.path().join("package.json"), r#"{"version": "1.4.0"}"#).unwrap();
+    std::fs::create_dir_all(tmp.path().join("packages/app")).unwrap();
+    std::fs::write(
+        tmp.path().join("packages/app/package.json"),
+        r#"{"name": "app", "version": "6.0.0"}"#,
+    )
+    .unwrap();
+    let content = "a = 'New in version 3.2'\nb = 'Since v4.1.2'\nc = 'Added in Version 5.0'\nd = 'See v3'\ne = 'nv3.2'\n";
+
+    let findings = analyze_in(
+        tmp.path(),
+        &CopyConfig::default(),
+        "packages/app/src/banner.py",
+        content,
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![at(1, "version_reference"), at(2, "version_reference")]
+    );
+    assert_eq!(
+        findings[1].message,
+        "Copy mentions version 4.1.2, 2 major version(s) behind 6.0.0"
+    );
+
+    // The root package is at 1.x
+    assert!(analyze_in(tmp.path(), &CopyConfig::default(), "src/a.py", content).is_empty());
+
+    // A larger lag allows more
+    let config = banned("version_lag = 2\n");
+    let findings = analyze_in(tmp.path(), &config, "packages/app/src/banner.py", content);
+    assert_eq!(lines(&findings), vec![at(1, "version_reference")]);
+}
+
+#[test]
+fn test_cargo_and_pyproject_versions() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(
+        tmp.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/cli\"]\n\n[workspace.package]\nversion = \"3.0.0\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(tmp.path().join("crates/cli/src")).unwrap();
+    std::fs::write(
+        tmp.path().join("crates/cli/Cargo.toml"),
+        "[package]\nname = \"cli\"\nversion.workspace = true\n",
+    )
+    .unwrap();
+    let findings = analyze_in(
+        tmp.path(),
+        &CopyConfig::default(),
+        "crates/cli/src/main.rs",
+        "fn main() { println!(\"new in version 1.2\"); }\n",
+    );
+    assert_eq!(lines(&findings), vec![at(1, "version_reference")]);
+
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(
+        tmp.path().join("pyproject.toml"),
+        "[tool.poetry]\nname = \"shop\"\nversion = \"4.2.0\"\n",
+    )
+    .unwrap();
+    let findings = analyze_in(
+        tmp.path(),
+        &CopyConfig::default(),
+        "shop/app.py",
+        "BANNER = 'new in v2.0'\n",
+    );
+    assert_eq!(lines(&findings), vec![at(1, "version_reference")]);
+}
+
+#[test]
+fn test_no_manifest_no_version_check() {
+    let findings = analyze(&CopyConfig::default(), "a.py", "a = 'New in version 0.1'\n");
+    assert!(findings.is_empty());
+}
+
+// ── Banned terms ─────────────────────────────────────────────────
+
+#[test]
+fn test_banned_terms_only_in_strings() {
+    let config = banned("banned = [{ term = \"Acme Cloud\", replacement = \"Nimbus\" }]\n");
+    let content = r#"package main
+
+// Acme Cloud client
+var AcmeCloud = "Acme Cloud"
+var label = "Acme Cloudy day"
+var raw = `Welcome to Acme Cloud`
+"#;
+    let findings = analyze(&config, "main.go", content);
+    assert_eq!(
+        lines(&findings),
+        vec![at(4, "banned_term"), at(6, "banned_term")]
+    );
+    match &findings[0].fix_kind {
+        Some(FixKind::ReplacePattern { find, replace }) => {
+            assert_eq!(find, "\\bAcme Cloud\\b");
+            assert_eq!(replace, "Nimbus");
+        }
+        other => panic!("unexpected fix: {:?}", other),
+    }
+    assert_eq!(
+        (findings[1].column, findings[1].end_column),
+        (Some(23), Some(33))
+    );
+}
+
+#[test]
+fn test_interpolated_code_is_not_text() {
+    let config = banned("banned = [{ term = \"OldBrand\" }]\n");
+    for (file, content) in [
+        ("a.py", "s = f\"Hello {OldBrand.name}\"\n"),
+        ("a.ts", "const s = `Hello ${OldBrand.name}`;\n"),
+        ("a.rb", "s = \"Hello #{OldBrand.name}\"\n"),
+        ("a.php", "<?php $s = \"Hello $OldBrand\";\n"),
+    ] {
+        assert!(analyze(&config, file, content).is_empty(), "{}", file);
+    }
+    let findings = analyze(&config, "a.py", "s = f\"OldBrand {name}\"\n");
+    assert_eq!(lines(&findings), vec![at(1, "banned_term")]);
+}
+
+#[test]
+fn test_banned_term_fix_and_replacement_escaping() {
+    let config =
+        banned("banned = [{ term = \"C++Kit\", replacement = \"$Kit\", severity = \"error\" }]\n");
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("a.py");
+    let content = "label = \"Built with C++Kit\"\n";
+    std::fs::write(&file, content).unwrap();
+    let findings = analyze_in(tmp.path(), &config, "a.py", content);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].severity, Severity::Error);
+
+    let log = TempDir::new().unwrap();
+    revet_core::fixer::apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&file).unwrap(),
+        "label = \"Built with $Kit\"\n"
+    );
+}
+
+#[test]
+fn test_documents_are_read_whole() {
+    let config = banned("banned = [{ term = \"OldBrand\", replacement = \"NewBrand\" }]\n");
+    let findings = analyze(
+        &config,
+        "docs/intro.md",
+        "# OldBrand\n\nOldBrandish is fine.\n\n<p>&copy; 2001 OldBrand</p>\n",
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![
+            at(1, "banned_term"),
+            at(5, "copyright_year"),
+            at(5, "banned_term"),
+        ]
+    );
+}
+
+// ── Config ───────────────────────────────────────────────────────
+
+#[test]
+fn test_allow_paths() {
+    let config =
+        banned("allow_paths = [\"docs/history/**\"]\nbanned = [{ term = \"OldBrand\" }]\n");
+    let content = "OldBrand\n";
+    assert!(analyze(&config, "docs/history/2019.md", content).is_empty());
+    assert_eq!(analyze(&config, "CHANGELOG.md", content).len(), 1);
+    // The defaults cover changelogs anywhere
+    let config = banned("banned = [{ term = \"OldBrand\" }]\n");
+    assert!(analyze(&config, "packages/ui/CHANGELOG.md", content).is_empty());
+    assert!(analyze(&config, "RELEASE_NOTES.md", content).is_empty());
+}
+
+#[test]
+fn test_severity_overrides() {
+    let config = banned("[severity]\ncopyright_year = \"error\"\nbanned_term = \"info\"\n");
+    let config = CopyConfig {
+        banned: banned("banned = [{ term = \"OldBrand\" }]\n").banned,
+        ..config
+    };
+    let findings = analyze(&config, "a.py", "a = \"© 2019 OldBrand\"\n");
+    let severities: Vec<Severity> = findings.iter().map(|f| f.severity).collect();
+    assert_eq!(severities, vec![Severity::Error, Severity::Info]);
+}
+
+#[test]
+fn test_sub_rule_selection() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(tmp.path().join("a.md"), "© 2019 OldBrand\n").unwrap();
+    let config: RevetConfig = toml::from_str(
+        "[modules]\ncopy = true\n\n[analyzers.copy]\nbanned = [{ term = \"OldBrand\" }]\ndisable = [\"copyright_year\"]\n",
+    )
+    .unwrap();
+    let findings = AnalyzerDispatcher::new_with_config(&config).run_all(
+        &[tmp.path().join("a.md")],
+        tmp.path(),
+        &config,
+    );
+    let rules: Vec<_> = findings.iter().filter_map(|f| f.rule.as_deref()).collect();
+    assert_eq!(rules, vec!["copy:banned_term"]);
+}
+
+#[test]
+fn test_config_validation() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[analyzers.copy]
+allow_paths = ["[unclosed"]
+only = ["copyright"]
+banned = [{ term = " " }, { term = "OldBrand", severity = "fatal" }]
+
+[analyzers.copy.severity]
+copyright_year = "loud"
+terms = "info"
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    let errors: Vec<&String> = errors.iter().filter(|e| e.contains("copy")).collect();
+    assert_eq!(errors.len(), 6, "{:?}", errors);
+    assert!(errors.iter().any(|e| e.contains("\"[unclosed\"")));
+    assert!(errors.iter().any(|e| e.contains("\"copyright\"")));
+    assert!(errors.iter().any(|e| e.contains("term must not be empty")));
+    assert!(errors.iter().any(|e| e.contains("severity \"fatal\"")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("copyright_year = \"loud\"")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("unknown sub-rule \"terms\"")));
+
+    assert!(RevetConfig::default()
+        .validate()
+        .0
+        .iter()
+        .all(|e| !e.contains("copy")));
+}
@@ -0,0 +1,243 @@
+//! Tests for the coupling analyzer: hand-built graphs whose envy ratios and
+//! pair densities are known
+
+use revet_core::analyzer::coupling::{find_envy, find_intimacy, CouplingAnalyzer};
+use revet_core::analyzer::GraphAnalyzer;
+use revet_core::config::RevetConfig;
+use revet_core::graph::{CodeGraph, Edge, EdgeKind, Node, NodeData, NodeId, NodeKind};
+use revet_core::{CodeKind, Severity};
+use std::path::{Path, PathBuf};
+
+const ROOT: &str = "/repo";
+
+fn config() -> RevetConfig {
+    let mut config = RevetConfig::default();
+    config.modules.coupling = true;
+    config
+}
+
+fn path(file: &str) -> PathBuf {
+    Path::new(ROOT).join(file)
+}
+
+fn file(graph: &mut CodeGraph, name: &str, code_kind: CodeKind) -> NodeId {
+    graph.add_node(Node::new(
+        NodeKind::File,
+        name.to_string(),
+        path(name),
+        0,
+        NodeData::File {
+            language: "python".to_string(),
+            code_kind,
+        },
+    ))
+}
+
+fn function(graph: &mut CodeGraph, file: &str, name: &str, line: usize) -> NodeId {
+    graph.add_node(Node::new(
+        NodeKind::Function,
+        name.to_string(),
+        path(file),
+        line,
+        NodeData::Function {
+            parameters: vec![],
+            return_type: None,
+        },
+    ))
+}
+
+fn calls(graph: &mut CodeGraph, from: NodeId, to: NodeId, times: usize) {
+    for _ in 0..times {
+        graph.add_edge(from, to, Edge::new(EdgeKind::Calls));
+    }
+}
+
+/// `orders.py::total` calls `billing.py` 7 times (tax 4, discount 3) and
+/// its own file once
+fn envy_graph(billing_kind: CodeKind) -> (CodeGraph, NodeId) {
+    let mut graph = CodeGraph::new(PathBuf::from(ROOT));
+    file(&mut graph, "orders.py", CodeKind::Production);
+    file(&mut graph, "billing.py", billing_kind);
+    let total = function(&mut graph, "orders.py", "total", 3);
+    let lines = function(&mut graph, "orders.py", "lines", 10);
+    let tax = function(&mut graph, "billing.py", "tax", 1);
+    let discount = function(&mut graph, "billing.py", "discount", 8);
+    calls(&mut graph, total, lines, 1);
+    calls(&mut graph, total, tax, 4);
+    calls(&mut graph, total, discount, 3);
+    (graph, total)
+}
+
+#[test]
+fn test_envy_counts_references_per_file() {
+    let (graph, total) = envy_graph(CodeKind::Production);
+    let envy = find_envy(&graph, &config());
+    assert_eq!(envy.len(), 1);
+    assert_eq!(envy[0].function, total);
+    assert_eq!((envy[0].foreign, envy[0].home), (7, 1));
+    assert_eq!(envy[0].file, path("billing.py"));
+    let counts: Vec<usize> = envy[0].symbols.iter().map(|&(_, n)| n).collect();
+    assert_eq!(counts, [4, 3]);
+
+    let findings = CouplingAnalyzer::new().analyze_graph(&graph, &config());
+    assert_eq!(findings.len(), 1);
+    let finding = &findings[0];
+    assert_eq!(finding.severity, Severity::Info);
+    assert_eq!(finding.rule.as_deref(), Some("envy"));
+    assert_eq!(finding.line, 3);
+    assert_eq!(
+        finding.message,
+        "Function `total` makes 7 references into `billing.py` and 1 into its own file"
+    );
+    assert_eq!(
+        finding.details.as_ref().unwrap().location_list,
+        ["billing.py:1", "billing.py:8"]
+    );
+}
+
+#[test]
+fn test_envy_needs_both_ratio_and_minimum() {
+    let (graph, _) = envy_graph(CodeKind::Production);
+
+    // 7 < 8 × 1
+    let mut strict = config();
+    strict.analyzers.coupling.envy_ratio = 8.0;
+    assert!(find_envy(&graph, &strict).is_empty());
+
+    let mut minimum = config();
+    minimum.analyzers.coupling.envy_min_refs = 8;
+    assert!(find_envy(&graph, &minimum).is_empty());
+
+    // Exactly at the ratio still counts
+    let mut exact = config();
+    exact.analyzers.coupling.envy_ratio = 7.0;
+    assert_eq!(find_envy(&graph, &exact).len(), 1);
+}
+
+#[test]
+fn test_test_and_vendored_files_are_left_out() {
+    let (graph, _) = envy_graph(CodeKind::Test);
+    assert!(find_envy(&graph, &config()).is_empty());
+
+    let (graph, _) = envy_graph(CodeKind::Production);
+    let mut vendored = config();
+    vendored.vendored = toml::from_str::<RevetConfig>("[[vendored]]\npath = \"billing.py\"\n")
+        .unwrap()
+        .vendored;
+    assert!(find_envy(&graph, &vendored).is_empty());
+
+    let mut globbed = config();
+    globbed.files.test_paths = vec!["billing.py".to_string()];
+    assert!(find_envy(&graph, &globbed).is_empty());
+}
+
+/// `a.py` (2 symbols) and `b.py` (2 symbols) call each other 6 and 4
+/// times; `c.py` (2 symbols) only calls `a.py`
+fn intimacy_graph() -> CodeGraph {
+    let mut graph = CodeGraph::new(PathBuf::from(ROOT));
+    for name in ["a.py", "b.py", "c.py"] {
+        file(&mut graph, name, CodeKind::Production);
+    }
+    let a1 = function(&mut graph, "a.py", "a1", 1);
+    let a2 = function(&mut graph, "a.py", "a2", 5);
+    let b1 = function(&mut graph, "b.py", "b1", 1);
+    let b2 = function(&mut graph, "b.py", "b2", 5);
+    let c1 = function(&mut graph, "c.py", "c1", 1);
+    let c2 = function(&mut graph, "c.py", "c2", 5);
+    calls(&mut graph, a1, b1, 4);
+    calls(&mut graph, a2, b2, 2);
+    calls(&mut graph, b1, a2, 2);
+    calls(&mut graph, b2, a2, 2);
+    calls(&mut graph, c1, a1, 1);
+    calls(&mut graph, c2, a1, 8);
+    graph
+}
+
+#[test]
+fn test_intimacy_density_over_shared_pairs() {
+    let graph = intimacy_graph();
+    let mut config = config();
+    // Keep envy out of the way
+    config.analyzers.coupling.envy_min_refs = 100;
+    let pairs = find_intimacy(&graph, &config);
+
+    // a ↔ b: 10 edges over 4 symbols; a ← c only goes one way
+    assert_eq!(pairs.len(), 1);
+    let pair = &pairs[0];
+    assert_eq!(pair.files, (path("a.py"), path("b.py")));
+    assert_eq!((pair.forward, pair.backward, pair.symbols), (6, 4, 4));
+    assert_eq!(pair.density, 2.5);
+    // a2 and b1 take part in 6 edges each, a1 and b2 in 4
+    let counts: Vec<usize> = pair.involved.iter().map(|&(_, n)| n).collect();
+    assert_eq!(counts, [6, 6, 4, 4]);
+
+    let findings = CouplingAnalyzer::new().analyze_graph(&graph, &config);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].rule.as_deref(), Some("intimacy"));
+    assert_eq!(findings[0].file, path("a.py"));
+    assert_eq!(
+        findings[0].message,
+        "`a.py` and `b.py` reference each other heavily: 6 edges one way and 4 back across 4 symbols (density 2.50)"
+    );
+    assert_eq!(
+        findings[0].details.as_ref().unwrap().location_list[0],
+        "a.py:5"
+    );
+
+    config.analyzers.coupling.intimacy_density = 2.6;
+    assert!(find_intimacy(&graph, &config).is_empty());
+    config.analyzers.coupling.intimacy_density = 0.5;
+    config.analyzers.coupling.intimacy_min_edges = 11;
+    assert!(find_intimacy(&graph, &config).is_empty());
+}
+
+#[test]
+fn test_intimate_pairs_are_ranked_and_capped() {
+    // Three pairs with 4, 6 and 8 edges over 4 symbols each
+    let mut graph = CodeGraph::new(PathBuf::from(ROOT));
+    for (i, edges) in [4, 6, 8].into_iter().enumerate() {
+        let (x, y) = (format!("x{}.py", i), format!("y{}.py", i));
+        file(&mut graph, &x, CodeKind::Production);
+        file(&mut graph, &y, CodeKind::Production);
+        let x1 = function(&mut graph, &x, "f", 1);
+        function(&mut graph, &x, "g", 2);
+        let y1 = function(&mut graph, &y, "f", 1);
+        function(&mut graph, &y, "g", 2);
+        calls(&mut graph, x1, y1, edges / 2);
+        calls(&mut graph, y1, x1, edges / 2);
+    }
+    let mut config = config();
+    config.analyzers.coupling.intimacy_min_edges = 1;
+    config.analyzers.coupling.envy_min_refs = 100;
+    config.analyzers.coupling.max_pairs = 2;
+
+    let pairs = find_intimacy(&graph, &config);
+    let densities: Vec<f64> = pairs.iter().map(|p| p.density).collect();
+    assert_eq!(densities, [2.0, 1.5]);
+    assert_eq!(pairs[0].files.0, path("x2.py"));
+    // Deterministic across runs
+    assert_eq!(find_intimacy(&graph, &config), pairs);
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let config = RevetConfig::default();
+    assert!(!CouplingAnalyzer::new().is_enabled(&config));
+    assert!(config.validate().0.is_empty());
+
+    let bad: RevetConfig = toml::from_str(
+        "[analyzers.coupling]\nenvy_ratio = 0.5\n[analyzers.coupling.severity]\nenvy = \"loud\"\n",
+    )
+    .unwrap();
+    let errors = bad.validate().0;
+    assert!(
+        errors.iter().any(|e| e.contains("envy_ratio = 0.5")),
+        "{:?}",
+        errors
+    );
+    assert!(
+        errors.iter().any(|e| e.contains("envy = \"loud\"")),
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,293 @@
+//! Tests for the analyzer coverage ledger
+
+use revet_core::{
+    discover_files_recorded, AnalyzerDispatcher, CoverageLedger, ParserDispatcher, RevetConfig,
+    SkipReason,
+};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn write(root: &Path, rel: &str, content: &[u8]) -> PathBuf {
+    let path = root.join(rel);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+/// Discovery, parsing and analyzer coverage, as `revet review --full` runs them
+fn run_pipeline(root: &Path, config: &RevetConfig) -> (Vec<PathBuf>, CoverageLedger) {
+    let parsers = ParserDispatcher::new_with_config(config);
+    let analyzers = AnalyzerDispatcher::new_with_config(config);
+    let mut extensions = parsers.supported_extensions();
+    for ext in analyzers.extra_extensions(config) {
+        if !extensions.contains(&ext) {
+            extensions.push(ext);
+        }
+    }
+    let filenames = analyzers.extra_filenames(config);
+    let dotfiles = analyzers.extra_dotfiles(config);
+
+    let mut ledger = CoverageLedger::new();
+    let files = discover_files_recorded(
+        root,
+        &extensions,
+        &filenames,
+        &dotfiles,
+        &config.ignore.paths,
+        &mut ledger,
+    )
+    .unwrap();
+
+    let (_, errors) = parsers.parse_files_parallel(&files, root.to_path_buf());
+    parsers.record_coverage(&files, &errors, &mut ledger);
+    analyzers.record_coverage(&files, config, &mut ledger);
+    analyzers.record_graph_coverage(&files, config, &mut ledger);
+    (files, ledger)
+}
+
+/// Files the walker can see: everything except `.git` and hidden paths
+fn visible_files(root: &Path) -> usize {
+    fn walk(dir: &Path, count: &mut usize) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let entry = entry.unwrap();
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            if entry.file_type().unwrap().is_dir() {
+                walk(&entry.path(), count);
+            } else {
+                *count += 1;
+            }
+        }
+    }
+    let mut count = 0;
+    walk(root, &mut count);
+    count
+}
+
+// ── Reconciliation ───────────────────────────────────────────────
+
+#[test]
+fn test_counts_reconcile_with_every_skip_reason() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    write(&root, "src/app.py", b"def main():\n    return 1\n");
+    write(&root, "src/util.ts", b"export const x = 1;\n");
+    write(&root, "proto/api.proto", b"syntax = \"proto3\";\n");
+    write(&root, "db/schema.sql", b"CREATE TABLE t (id int);\n");
+    write(&root, "vendor/dep.py", b"x = 1\n");
+    write(&root, "vendor/dep.js", b"var x = 1;\n");
+    write(&root, "src/binary.py", b"\xff\xfe\x00 not utf-8\n");
+    write(&root, "src/notes.rb", b"puts 'hi'\n");
+
+    let mut config = RevetConfig::default();
+    config.ignore.paths = vec!["vendor/".to_string()];
+
+    let (files, ledger) = run_pipeline(&root, &config);
+    let summary = ledger.summary();
+
+    assert_eq!(summary.discovered, visible_files(&root));
+    assert_eq!(summary.discovered, 8);
+    assert_eq!(
+        summary.analyzed + summary.skipped_count(),
+        summary.discovered
+    );
+    assert_eq!(files.len(), 4, "handed on for analysis: {files:?}");
+
+    let count = |reason| {
+        summary
+            .skipped
+            .iter()
+            .find(|g| g.reason == reason)
+            .map_or(0, |g| g.count)
+    };
+    assert_eq!(count(SkipReason::UnsupportedExtension), 2);
+    assert_eq!(count(SkipReason::ExcludedByConfig), 2);
+    assert_eq!(count(SkipReason::Unreadable), 1);
+    assert_eq!(summary.analyzed, 3);
+
+    let unsupported = summary
+        .skipped
+        .iter()
+        .find(|g| g.reason == SkipReason::UnsupportedExtension)
+        .unwrap();
+    assert_eq!(unsupported.extensions, vec![".proto", ".sql"]);
+}
+
+#[test]
+fn test_no_enabled_analyzer() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    let app = write(&root, "app.py", b"x = 1\n");
+
+    let mut config = RevetConfig::default();
+    config.modules.security = false;
+    config.modules.ml = false;
+    config.modules.cycles = false;
+    config.modules.upgrade = false;
+
+    let (_, ledger) = run_pipeline(&root, &config);
+    assert_eq!(
+        ledger.skip_reason(&app),
+        Some(SkipReason::NoEnabledAnalyzer)
+    );
+
+    let report = ledger.report(&root, true);
+    assert!(report.files[0].disabled.contains(&"SEC".to_string()));
+    assert!(report.files[0].analyzers.is_empty());
+}
+
+#[test]
+fn test_parse_error_without_content_analyzer() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    let app = write(&root, "app.py", b"x = 1\n");
+    let files = vec![app.clone()];
+
+    let mut ledger = CoverageLedger::new();
+    ledger.discovered(&app);
+    let errors = vec![format!("{}: Failed to parse file: boom", app.display())];
+    ParserDispatcher::new().record_coverage(&files, &errors, &mut ledger);
+
+    assert!(!ledger.is_parsed(&app));
+    assert_eq!(ledger.skip_reason(&app), Some(SkipReason::ParseError));
+
+    // A content analyzer still scanning the file makes it analyzed
+    ledger.analyzed_by(&app, "SEC");
+    assert_eq!(ledger.skip_reason(&app), None);
+}
+
+// ── Per-file detail ──────────────────────────────────────────────
+
+#[test]
+fn test_analyzers_recorded_per_file() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    let app = write(&root, "app.py", b"import os\n");
+    let tf = write(&root, "main.tf", b"resource \"x\" \"y\" {}\n");
+
+    let mut config = RevetConfig::default();
+    config.modules.infra = true;
+
+    let (_, ledger) = run_pipeline(&root, &config);
+
+    let py = ledger.analyzers(&app);
+    assert!(py.contains(&"SEC".to_string()));
+    assert!(py.contains(&"CYCLE".to_string()), "graph analyzer: {py:?}");
+    assert!(!py.contains(&"INFRA".to_string()));
+
+    let tf = ledger.analyzers(&tf);
+    assert!(tf.contains(&"INFRA".to_string()), "got {tf:?}");
+    assert!(!tf.contains(&"CYCLE".to_string()), "not parsed: {tf:?}");
+}
+
+#[test]
+fn test_report_paths_are_relative() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    write(&root, "src/app.py", b"x = 1\n");
+    write(&root, "docs/api.proto", b"syntax = \"proto3\";\n");
+
+    let (_, ledger) = run_pipeline(&root, &RevetConfig::default());
+
+    let summary_only = ledger.report(&root, false);
+    assert!(summary_only.files.is_empty());
+
+    let full = ledger.report(&root, true);
+    let paths: Vec<&Path> = full.files.iter().map(|f| f.path.as_path()).collect();
+    assert_eq!(
+        paths,
+        vec![Path::new("docs/api.proto"), Path::new("src/app.py")]
+    );
+    assert_eq!(
+        full.files[0].skipped,
+        Some(SkipReason::UnsupportedExtension)
+    );
+    assert_eq!(full.files[1].skipped, None);
+}
+
+#[test]
+fn test_large_report_groups_by_directory_and_extension() {
+    let root = PathBuf::from("/repo");
+    let mut ledger = CoverageLedger::new();
+    for i in 0..revet_core::coverage::FILE_DETAIL_LIMIT {
+        let path = root.join(format!("src/f{i}.py"));
+        ledger.discovered(&path);
+        ledger.analyzed_by(&path, "SEC");
+    }
+    ledger.dropped(&root.join("src/a.proto"), SkipReason::UnsupportedExtension);
+
+    let report = ledger.report(&root, true);
+    assert!(report.files.is_empty());
+    assert_eq!(report.groups.len(), 2);
+
+    let py = report.groups.iter().find(|g| g.extension == ".py").unwrap();
+    assert_eq!(py.directory, PathBuf::from("src"));
+    assert_eq!(py.files, revet_core::coverage::FILE_DETAIL_LIMIT);
+    assert_eq!(py.analyzed, py.files);
+    assert_eq!(py.analyzers["SEC"], py.files);
+
+    let proto = report
+        .groups
+        .iter()
+        .find(|g| g.extension == ".proto")
+        .unwrap();
+    assert_eq!(proto.skipped[&SkipReason::UnsupportedExtension], 1);
+}
+
+// ── Summary line ─────────────────────────────────────────────────
+
+#[test]
+fn test_summary_line() {
+    let root = PathBuf::from("/repo");
+    let mut ledger = CoverageLedger::new();
+    for i in 0..1234 {
+        ledger.analyzed_by(&root.join(format!("f{i}.py")), "SEC");
+    }
+    for i in 0..120 {
+        let ext = if i % 2 == 0 { "proto" } else { "sql" };
+        ledger.dropped(
+            &root.join(format!("u{i}.{ext}")),
+            SkipReason::UnsupportedExtension,
+        );
+    }
+    for i in 0..40 {
+        ledger.dropped(
+            &root.join(format!("vendor/v{i}.py")),
+            SkipReason::ExcludedByConfig,
+        );
+    }
+    for i in 0..16 {
+        ledger.parse_failed(&root.join(format!("p{i}.py")), "boom");
+    }
+
+    assert_eq!(
+        ledger.summary().to_string(),
+        "analyzed 1,234 of 1,410 discovered files; 176 skipped \u{2014} \
+         120 unsupported extension (.proto, .sql), 40 excluded by config, 16 parse errors"
+    );
+}
+
+#[test]
+fn test_summary_line_without_skips() {
+    let mut ledger = CoverageLedger::new();
+    ledger.analyzed_by(Path::new("/repo/a.py"), "SEC");
+    assert_eq!(
+        ledger.summary().to_string(),
+        "analyzed 1 of 1 discovered file"
+    );
+}
+
+#[test]
+fn test_json_shape() {
+    let mut ledger = CoverageLedger::new();
+    ledger.analyzed_by(Path::new("/repo/a.py"), "SEC");
+    ledger.dropped(Path::new("/repo/b.proto"), SkipReason::UnsupportedExtension);
+
+    let json = serde_json::to_value(ledger.report(Path::new("/repo"), true)).unwrap();
+    assert_eq!(json["discovered"], 2);
+    assert_eq!(json["analyzed"], 1);
+    assert_eq!(json["skipped"][0]["reason"], "unsupported_extension");
+    assert_eq!(json["files"][0]["analyzers"][0], "SEC");
+    assert_eq!(json["files"][1]["skipped"], "unsupported_extension");
+}
@@ -562,3 +562,74 @@ fn test_rust_use_import_call_creates_calls_edge() {
     assert_eq!(callee.name(), "helper");
     assert!(callee.file_path().ends_with("utils.rs"));
 }
+
+// ── External calls ─────────────────────────────────────────────────────────
+
+/// `(caller, import module, callee, line)` of every external call edge
+fn external_calls(graph: &revet_core::CodeGraph) -> Vec<(String, String, String, usize)> {
+    let mut calls = Vec::new();
+    for (id, node) in graph.nodes() {
+        for (target, edge) in graph.edges_from(id) {
+            let Some(EdgeMetadata::ExternalCall { line, callee }) = edge.metadata() else {
+                continue;
+            };
+            assert_eq!(edge.kind(), &EdgeKind::Calls);
+            let import = graph.node(target).unwrap();
+            let NodeData::Import { module, .. } = import.data() else {
+                panic!("external call to {:?}", import.kind());
+            };
+            calls.push((
+                node.name().to_string(),
+                module.clone(),
+                callee.clone(),
+                *line,
+            ));
+        }
+    }
+    calls.sort();
+    calls
+}
+
+#[test]
+fn test_python_calls_into_external_modules_link_to_the_import() {
+    let dir = TempDir::new().unwrap();
+    let utils = write(&dir, "utils.py", "def helper(): pass\n");
+    let main = write(
+        &dir,
+        "main.py",
+        "import asyncio\nfrom os.path import join\nfrom utils import helper\n\n\
+         def run():\n    loop = asyncio.get_event_loop()\n    helper()\n    return join('a', 'b')\n",
+    );
+
+    let dispatcher = ParserDispatcher::new();
+    let (graph, _) = dispatcher.parse_files_parallel(&[utils, main], dir.path().to_path_buf());
+    assert_eq!(
+        external_calls(&graph),
+        vec![
+            ("run".into(), "asyncio".into(), "get_event_loop".into(), 6),
+            ("run".into(), "os.path".into(), "join".into(), 8),
+        ]
+    );
+}
+
+#[test]
+fn test_typescript_calls_into_node_modules_link_to_the_import() {
+    let dir = TempDir::new().unwrap();
+    let main = write(
+        &dir,
+        "main.ts",
+        "import url from 'url';\nimport * as crypto from 'crypto';\nimport { readFileSync } from 'node:fs';\n\n\
+         export function run(input: string) {\n  const parsed = url.parse(input);\n  readFileSync(input);\n  return crypto.createHash('sha256');\n}\n",
+    );
+
+    let dispatcher = ParserDispatcher::new();
+    let (graph, _) = dispatcher.parse_files_parallel(&[main], dir.path().to_path_buf());
+    assert_eq!(
+        external_calls(&graph),
+        vec![
+            ("run".into(), "crypto".into(), "createHash".into(), 8),
+            ("run".into(), "node:fs".into(), "readFileSync".into(), 7),
+            ("run".into(), "url".into(), "parse".into(), 6),
+        ]
+    );
+}
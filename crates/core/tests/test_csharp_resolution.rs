@@ -0,0 +1,357 @@
+//! Tests for C# cross-file resolution: partial types, top-level statements
+//! and source-generated members (`[parser.csharp.generated]`)
+
+use revet_core::analyzer::unused_exports::UnusedExportsAnalyzer;
+use revet_core::analyzer::GraphAnalyzer;
+use revet_core::config::RevetConfig;
+use revet_core::discovery::discover_files;
+use revet_core::graph::{EdgeKind, NodeData, NodeId, NodeKind};
+use revet_core::parser::csharp::ENTRY_POINT;
+use revet_core::parser::csharp_generated::generator;
+use revet_core::parser::python_frameworks::entry_point;
+use revet_core::{CodeGraph, ParserDispatcher};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn parse(root: &Path, config: &RevetConfig) -> CodeGraph {
+    let dispatcher = ParserDispatcher::new_with_config(config);
+    let files = discover_files(root, &dispatcher.supported_extensions(), &[]).unwrap();
+    let (graph, errors) = dispatcher.parse_files_parallel(&files, root.to_path_buf());
+    assert!(errors.is_empty(), "errors: {:?}", errors);
+    graph
+}
+
+fn copy_tree(from: &Path, to: &Path) {
+    for entry in std::fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            std::fs::create_dir_all(&target).unwrap();
+            copy_tree(&entry.path(), &target);
+        } else {
+            std::fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+/// The fixture, copied out of the repository's own `tests/` directory so
+/// its application code is not mistaken for tests
+fn fixture() -> (TempDir, CodeGraph, RevetConfig) {
+    let dir = TempDir::new().unwrap();
+    copy_tree(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/dotnet_app"),
+        dir.path(),
+    );
+    let config = RevetConfig::from_file(&dir.path().join(".revet.toml")).unwrap();
+    let graph = parse(dir.path(), &config);
+    (dir, graph, config)
+}
+
+fn repo(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (name, content) in files {
+        let path = dir.path().join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+fn named(graph: &CodeGraph, kind: NodeKind, name: &str) -> Vec<NodeId> {
+    graph
+        .nodes()
+        .filter(|(_, n)| n.kind() == &kind && n.name() == name)
+        .map(|(id, _)| id)
+        .collect()
+}
+
+fn one(graph: &CodeGraph, kind: NodeKind, name: &str) -> NodeId {
+    let found = named(graph, kind, name);
+    assert_eq!(found.len(), 1, "{:?} nodes named {}", kind, name);
+    found[0]
+}
+
+/// Names of the nodes `from` has `kind` edges to
+fn targets(graph: &CodeGraph, from: NodeId, kind: EdgeKind) -> Vec<String> {
+    let mut names: Vec<String> = graph
+        .edges_from(from)
+        .filter(|(_, e)| e.kind() == &kind)
+        .map(|(t, _)| graph.node(t).unwrap().name().to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn unused(graph: &CodeGraph, config: &RevetConfig) -> Vec<String> {
+    let mut names: Vec<String> = UnusedExportsAnalyzer::new()
+        .analyze_graph(graph, config)
+        .iter()
+        .map(|f| {
+            let start = f.message.find('`').unwrap() + 1;
+            let end = start + f.message[start..].find('`').unwrap();
+            f.message[start..end].to_string()
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+// ── Fixture ──────────────────────────────────────────────────────
+
+#[test]
+fn test_fixture_has_no_false_unused_exports() {
+    let (_dir, graph, config) = fixture();
+    assert_eq!(unused(&graph, &config), vec!["LegacyExporter"]);
+}
+
+#[test]
+fn test_partial_class_is_one_node_contained_by_both_files() {
+    let (_dir, graph, _) = fixture();
+    let order = one(&graph, NodeKind::Class, "Order");
+
+    let mut files: Vec<String> = graph
+        .edges_to(order)
+        .into_iter()
+        .filter(|(_, e)| e.kind() == &EdgeKind::Contains)
+        .map(|(file, _)| graph.node(file).unwrap().name().to_string())
+        .collect();
+    files.sort();
+    assert_eq!(files, vec!["Order.Validation.cs", "Order.cs"]);
+
+    let NodeData::Class { methods, .. } = graph.node(order).unwrap().data() else {
+        panic!("Order is a class");
+    };
+    for method in ["Create", "Submit", "Normalize", "Validate", "IdPattern"] {
+        assert!(methods.iter().any(|m| m == method), "{:?}", methods);
+    }
+
+    // The lookup index survives the removal of the second declaration
+    for (id, node) in graph.nodes() {
+        assert!(
+            graph
+                .find_nodes(node.file_path(), Some(node.name()))
+                .contains(&id),
+            "{} is not indexed",
+            node.name()
+        );
+    }
+}
+
+#[test]
+fn test_calls_between_partial_declarations() {
+    let (_dir, graph, _) = fixture();
+    let submit = one(&graph, NodeKind::Function, "Order.Submit");
+    let validate = one(&graph, NodeKind::Function, "Order.Validate");
+    assert_eq!(
+        targets(&graph, submit, EdgeKind::Calls),
+        vec!["Order.Validate"]
+    );
+    assert_eq!(
+        targets(&graph, validate, EdgeKind::Calls),
+        vec!["Order.IdPattern", "Order.Normalize"]
+    );
+    // A call within the type does not count as a use of the type
+    assert!(targets(&graph, submit, EdgeKind::References).is_empty());
+}
+
+#[test]
+fn test_top_level_statements_are_one_entry_point() {
+    let (_dir, graph, _) = fixture();
+    let main = one(&graph, NodeKind::Function, ENTRY_POINT);
+    let node = graph.node(main).unwrap();
+    assert!(node.file_path().ends_with("Program.cs"));
+    assert_eq!((node.line(), node.end_line()), (5, Some(15)));
+
+    assert_eq!(
+        targets(&graph, main, EdgeKind::Calls),
+        vec![
+            "CartViewModel",
+            "EmailValidator.IsValid",
+            "Order.Create",
+            "OrderService"
+        ]
+    );
+    assert_eq!(
+        targets(&graph, main, EdgeKind::References),
+        vec!["CartViewModel", "EmailValidator", "Order", "OrderService"]
+    );
+}
+
+#[test]
+fn test_generated_regex_methods_are_tagged() {
+    let (_dir, graph, _) = fixture();
+    for name in ["EmailValidator.EmailPattern", "Order.IdPattern"] {
+        let node = graph.node(one(&graph, NodeKind::Function, name)).unwrap();
+        assert_eq!(generator(node), Some("regex"), "{}", name);
+    }
+    let is_valid = one(&graph, NodeKind::Function, "EmailValidator.IsValid");
+    assert_eq!(
+        targets(&graph, is_valid, EdgeKind::Calls),
+        vec!["EmailValidator.EmailPattern"]
+    );
+}
+
+#[test]
+fn test_mvvm_members_are_synthesized() {
+    let (_dir, graph, _) = fixture();
+    let property = one(&graph, NodeKind::Function, "CartViewModel.ItemCount");
+    let command = one(&graph, NodeKind::Function, "CartViewModel.ClearCommand");
+    assert_eq!(
+        generator(graph.node(property).unwrap()),
+        Some("mvvm_property")
+    );
+    assert_eq!(
+        generator(graph.node(command).unwrap()),
+        Some("mvvm_command")
+    );
+    assert_eq!(
+        targets(&graph, property, EdgeKind::References),
+        vec!["_itemCount"]
+    );
+    assert_eq!(
+        targets(&graph, command, EdgeKind::References),
+        vec!["CartViewModel.Clear"]
+    );
+
+    let class = one(&graph, NodeKind::Class, "CartViewModel");
+    let NodeData::Class { methods, .. } = graph.node(class).unwrap().data() else {
+        panic!("CartViewModel is a class");
+    };
+    assert!(methods.contains(&"ItemCount".to_string()));
+    assert!(methods.contains(&"ClearCommand".to_string()));
+}
+
+#[test]
+fn test_grpc_service_is_an_entry_point() {
+    let (_dir, graph, _) = fixture();
+    let service = one(&graph, NodeKind::Class, "GreeterService");
+    assert_eq!(entry_point(graph.node(service).unwrap()), Some("grpc"));
+}
+
+// ── Partial types ────────────────────────────────────────────────
+
+#[test]
+fn test_partial_types_in_different_namespaces_stay_apart() {
+    let dir = repo(&[
+        (
+            "A/Mapper.cs",
+            "namespace A\n{\n    public partial class Mapper\n    {\n        public void Map() { }\n    }\n}\n",
+        ),
+        (
+            "B/Mapper.cs",
+            "namespace B\n{\n    public partial class Mapper\n    {\n        public void Map() { }\n    }\n}\n",
+        ),
+        (
+            "B/Mapper.Extra.cs",
+            "namespace B\n{\n    public partial class Mapper\n    {\n        public void Extra() { Map(); }\n    }\n}\n",
+        ),
+    ]);
+    let graph = parse(dir.path(), &RevetConfig::default());
+    let mut declared: Vec<PathBuf> = named(&graph, NodeKind::Class, "Mapper")
+        .into_iter()
+        .map(|id| {
+            let path = graph.node(id).unwrap().file_path();
+            path.strip_prefix(dir.path()).unwrap().to_path_buf()
+        })
+        .collect();
+    declared.sort();
+    assert_eq!(
+        declared,
+        vec![PathBuf::from("A/Mapper.cs"), PathBuf::from("B/Mapper.cs")]
+    );
+}
+
+#[test]
+fn test_non_partial_types_are_not_merged() {
+    let dir = repo(&[
+        ("One/Config.cs", "public class Config { }\n"),
+        ("Two/Config.cs", "public class Config { }\n"),
+    ]);
+    let graph = parse(dir.path(), &RevetConfig::default());
+    assert_eq!(named(&graph, NodeKind::Class, "Config").len(), 2);
+}
+
+// ── Configured generators ────────────────────────────────────────
+
+const FORM: &str = r#"namespace Shop;
+
+public partial class SignupForm
+{
+    [GenerateValidator]
+    private string _email;
+}
+"#;
+
+const SUBMIT: &str = r#"namespace Shop;
+
+public class Signup
+{
+    public void Submit()
+    {
+        SignupForm.ValidateEmail();
+    }
+}
+"#;
+
+#[test]
+fn test_configured_generator() {
+    let dir = repo(&[("SignupForm.cs", FORM), ("Signup.cs", SUBMIT)]);
+    let config: RevetConfig = toml::from_str(
+        r#"
+[parser.csharp.generated.validation]
+attributes = ["GenerateValidator"]
+members    = ["Validate{Name}"]
+"#,
+    )
+    .unwrap();
+    let graph = parse(dir.path(), &config);
+    let validate = one(&graph, NodeKind::Function, "SignupForm.ValidateEmail");
+    assert_eq!(generator(graph.node(validate).unwrap()), Some("validation"));
+    let submit = one(&graph, NodeKind::Function, "Signup.Submit");
+    assert_eq!(
+        targets(&graph, submit, EdgeKind::Calls),
+        vec!["SignupForm.ValidateEmail"]
+    );
+
+    // Without the entry, nothing is generated and the call stays unresolved
+    let graph = parse(dir.path(), &RevetConfig::default());
+    assert!(named(&graph, NodeKind::Function, "SignupForm.ValidateEmail").is_empty());
+    let submit = one(&graph, NodeKind::Function, "Signup.Submit");
+    assert!(targets(&graph, submit, EdgeKind::Calls).is_empty());
+}
+
+#[test]
+fn test_configured_entry_replaces_builtin() {
+    let dir = repo(&[(
+        "GreeterService.cs",
+        "public class GreeterService : Greeter.GreeterBase { }\n",
+    )]);
+    let config: RevetConfig = toml::from_str("[parser.csharp.generated.grpc]\n").unwrap();
+    let graph = parse(dir.path(), &config);
+    let service = one(&graph, NodeKind::Class, "GreeterService");
+    assert_eq!(entry_point(graph.node(service).unwrap()), None);
+    assert_eq!(unused(&graph, &config), vec!["GreeterService"]);
+}
+
+#[test]
+fn test_config_validation() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[parser.csharp.generated.forms]
+attributes = ["[GenerateValidator]", "Forms.*"]
+bases      = ["*.FormBase<T>"]
+members    = ["Validate{Name}", "{Name}.Changed"]
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    let errors: Vec<&String> = errors
+        .iter()
+        .filter(|e| e.starts_with("[parser.csharp.generated.forms]"))
+        .collect();
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors[0].contains("\"[GenerateValidator]\""));
+    assert!(errors[1].contains("\"*.FormBase<T>\""));
+    assert!(errors[2].contains("members entry \"{Name}.Changed\""));
+}
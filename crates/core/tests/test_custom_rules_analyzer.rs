@@ -1,5 +1,7 @@
 //! Tests for the custom rules analyzer
 
+mod common;
+
 use revet_core::{AnalyzerDispatcher, Finding, RevetConfig, Severity};
 use std::io::Write;
 use std::path::PathBuf;
@@ -505,7 +507,7 @@ fix_replace = 'logger.info('
 "#,
     );
 
-    let findings = run_custom(&config, &[file.clone()], dir.path());
+    let findings = run_custom(&config, std::slice::from_ref(&file), dir.path());
     assert_eq!(findings.len(), 1);
 
     // fix_kind should be ReplacePattern, not Suggestion
@@ -519,7 +521,9 @@ fix_replace = 'logger.info('
     }
 
     // Actually apply the fix
-    revet_core::apply_fixes(&findings).expect("fix should succeed");
+    let log = TempDir::new().unwrap();
+    revet_core::apply_fixes(&findings, &mut common::journal(log.path()))
+        .expect("fix should succeed");
     let fixed = std::fs::read_to_string(&file).unwrap();
     assert_eq!(fixed, "logger.info('hello');\n");
 }
@@ -549,10 +553,12 @@ fix_replace = 'logger.info'
 "#,
     );
 
-    let findings = run_custom(&config, &[file.clone()], dir.path());
+    let findings = run_custom(&config, std::slice::from_ref(&file), dir.path());
     assert_eq!(findings.len(), 2);
 
-    revet_core::apply_fixes(&findings).expect("fix should succeed");
+    let log = TempDir::new().unwrap();
+    revet_core::apply_fixes(&findings, &mut common::journal(log.path()))
+        .expect("fix should succeed");
     let fixed = std::fs::read_to_string(&file).unwrap();
     assert_eq!(fixed, "logger.info('a');\nlet x = 1;\nlogger.info('b');\n");
 }
@@ -631,7 +637,7 @@ fix_replace = 'logger.info'
 "#,
     );
 
-    let findings = run_custom(&config, &[file.clone()], dir.path());
+    let findings = run_custom(&config, std::slice::from_ref(&file), dir.path());
     assert_eq!(findings.len(), 1);
     // fix_kind should still be ReplacePattern even without suggestion
     assert!(matches!(
@@ -639,7 +645,9 @@ fix_replace = 'logger.info'
         Some(revet_core::FixKind::ReplacePattern { .. })
     ));
 
-    revet_core::apply_fixes(&findings).expect("fix should succeed");
+    let log = TempDir::new().unwrap();
+    revet_core::apply_fixes(&findings, &mut common::journal(log.path()))
+        .expect("fix should succeed");
     let fixed = std::fs::read_to_string(&file).unwrap();
     assert_eq!(fixed, "logger.info('x');\n");
 }
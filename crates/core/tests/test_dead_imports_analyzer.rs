@@ -305,3 +305,41 @@ fn test_disabled_produces_no_findings() {
         "Disabled module should produce no findings"
     );
 }
+
+// ── Suggested code ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_suggests_import_without_the_unused_name() {
+    let cases = [
+        (
+            "from os import path, sep\n\nprint(sep)\n",
+            ".py",
+            vec!["path", "sep"],
+            Some("from os import sep"),
+        ),
+        (
+            "import React, { useState, useMemo } from 'react';\n\nuseState();\nReact.x();\n",
+            ".tsx",
+            vec!["React", "useState", "useMemo"],
+            Some("import React, { useState } from 'react';"),
+        ),
+        // Removing the only name is a line deletion, not code
+        ("import os\n\nprint(1)\n", ".py", vec!["os"], None),
+    ];
+    for (src, suffix, names, expected) in cases {
+        let tmp = write_temp(src, suffix);
+        let path = tmp.path().to_str().unwrap().to_string();
+        let mut graph = CodeGraph::new(PathBuf::from("."));
+        add_import_node(&mut graph, &path, 1, "m", names);
+
+        let findings = run(&graph);
+        assert_eq!(findings.len(), 1, "{findings:?}");
+        assert_eq!(
+            findings[0]
+                .suggestion_code
+                .as_ref()
+                .map(|code| code.snippet.as_str()),
+            expected
+        );
+    }
+}
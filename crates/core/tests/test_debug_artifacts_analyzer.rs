@@ -0,0 +1,495 @@
+//! Integration tests for DebugArtifactsAnalyzer
+
+mod common;
+
+use revet_core::analyzer::debug_artifacts::DebugArtifactsAnalyzer;
+use revet_core::analyzer::Analyzer;
+use revet_core::config::{DebugArtifactsConfig, RevetConfig};
+use revet_core::finding::{Confidence, Finding, FixKind, Severity};
+use std::path::Path;
+use tempfile::TempDir;
+
+const FIXTURE: &str = "debug_artifacts";
+const EXTENSIONS: &[&str] = &[".py", ".ts", ".js", ".rb", ".php", ".rs", ".go"];
+
+/// (repo-relative file, line, sub-rule) for each DBG finding, sorted
+fn summary(findings: &[Finding], root: &Path) -> Vec<(String, usize, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("DBG"))
+        .map(|f| {
+            let file = f.file.strip_prefix(root).unwrap_or(&f.file);
+            (
+                file.to_string_lossy().replace('\\', "/"),
+                f.line,
+                f.rule.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+fn row(file: &str, line: usize, rule: &str) -> (String, usize, String) {
+    (file.to_string(), line, format!("debug_artifacts:{}", rule))
+}
+
+fn analyze(file: &str, content: &str) -> Vec<Finding> {
+    analyze_with(&DebugArtifactsConfig::default(), file, content)
+}
+
+fn analyze_with(config: &DebugArtifactsConfig, file: &str, content: &str) -> Vec<Finding> {
+    DebugArtifactsAnalyzer::with_config(config).analyze_source(
+        Path::new(file),
+        content,
+        Path::new("."),
+    )
+}
+
+/// (line, sub-rule) pairs
+fn lines(findings: &[Finding]) -> Vec<(usize, String)> {
+    findings
+        .iter()
+        .map(|f| (f.line, f.rule.clone().unwrap_or_default()))
+        .collect()
+}
+
+fn at(line: usize, rule: &str) -> (usize, String) {
+    (line, rule.to_string())
+}
+
+// ── Fixture ──────────────────────────────────────────────────────
+
+#[test]
+fn test_fixture() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    assert_eq!(
+        summary(&findings, &root),
+        vec![
+            row("app/orders.py", 7, "debugger"),
+            row("app/orders.py", 8, "debug_print"),
+            row("app/orders.py", 12, "debug_print"),
+            row("app/orders.py", 19, "debugger"),
+            row("app/orders.py", 21, "commented_code"),
+            row("cmd/server.go", 11, "debugger"),
+            row("cmd/server.go", 12, "debug_print"),
+            row("cmd/server.go", 13, "debug_print"),
+            row("lib/report.rb", 10, "debugger"),
+            row("lib/report.rb", 11, "debug_print"),
+            row("lib/report.rb", 12, "debug_print"),
+            row("lib/report.rb", 15, "debugger"),
+            row("public/export.php", 5, "debugger"),
+            row("public/export.php", 6, "debug_print"),
+            row("public/export.php", 7, "debug_print"),
+            row("src/main.rs", 11, "debugger"),
+            row("src/main.rs", 12, "debug_print"),
+            row("src/main.rs", 15, "debugger"),
+            row("src/main.rs", 16, "commented_code"),
+            row("web/cart.ts", 5, "debugger"),
+            row("web/cart.ts", 6, "debug_print"),
+            row("web/cart.ts", 7, "debug_print"),
+            row("web/legacy.js", 7, "commented_code"),
+        ]
+    );
+}
+
+#[test]
+fn test_fixture_messages_severities_and_confidence() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let at = |file: &str, line: usize| {
+        findings
+            .iter()
+            .find(|f| f.file == root.join(file) && f.line == line)
+            .unwrap()
+    };
+
+    let debugger = at("app/orders.py", 7);
+    assert_eq!(
+        debugger.message,
+        "Debugger statement `breakpoint` left in the code"
+    );
+    assert_eq!(debugger.severity, Severity::Error);
+    assert_eq!(debugger.confidence, Confidence::High);
+    assert_eq!((debugger.column, debugger.end_column), (Some(5), Some(17)));
+
+    let print = at("web/cart.ts", 6);
+    assert_eq!(
+        print.message,
+        "`console.log` looks like leftover print debugging"
+    );
+    assert_eq!(print.severity, Severity::Warning);
+    assert_eq!(print.confidence, Confidence::Medium);
+
+    let block = at("web/legacy.js", 7);
+    assert_eq!(block.message, "7 lines of commented-out code (lines 7–13)");
+    assert_eq!(block.severity, Severity::Info);
+    assert_eq!(block.confidence, Confidence::Medium);
+}
+
+#[test]
+fn test_fixture_fixes_only_standalone_debuggers() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let fix = |file: &str, line: usize| {
+        findings
+            .iter()
+            .find(|f| f.file == root.join(file) && f.line == line)
+            .unwrap()
+            .fix_kind
+            .clone()
+    };
+    let statement = |fix: Option<FixKind>| match fix {
+        Some(FixKind::RemoveStatement { statement }) => Some(statement),
+        _ => None,
+    };
+
+    assert_eq!(
+        statement(fix("app/orders.py", 7)),
+        Some("breakpoint()".into())
+    );
+    assert_eq!(statement(fix("web/cart.ts", 5)), Some("debugger;".into()));
+    assert_eq!(statement(fix("lib/report.rb", 15)), Some("byebug".into()));
+    assert_eq!(
+        statement(fix("public/export.php", 5)),
+        Some("dd($users);".into())
+    );
+    assert_eq!(
+        statement(fix("src/main.rs", 11)),
+        Some("dbg!(&config);".into())
+    );
+    // Shares its line with an import
+    assert!(fix("app/orders.py", 19).is_none());
+    // Its value is used, and its argument does work
+    assert!(fix("src/main.rs", 15).is_none());
+    // Prints and comments are left to the author
+    assert!(fix("app/orders.py", 8).is_none());
+    assert!(fix("web/legacy.js", 7).is_none());
+}
+
+#[test]
+fn test_fixture_fix_applies() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let tmp = TempDir::new().unwrap();
+    let copy = tmp.path().join("report.rb");
+    std::fs::copy(root.join("lib/report.rb"), &copy).unwrap();
+    let fixable: Vec<Finding> = findings
+        .into_iter()
+        .filter(|f| f.file == root.join("lib/report.rb"))
+        .map(|f| Finding {
+            file: copy.clone(),
+            ..f
+        })
+        .collect();
+
+    let log = TempDir::new().unwrap();
+    let report =
+        revet_core::fixer::apply_fixes(&fixable, &mut common::journal(log.path())).unwrap();
+    assert_eq!(report.applied, 2);
+    let content = std::fs::read_to_string(&copy).unwrap();
+    assert!(!content.contains("binding.pry"));
+    assert!(!content.contains("byebug"));
+    assert!(content.contains("  def render\n    puts \"asdf\"\n"));
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let analyzer = DebugArtifactsAnalyzer::new();
+    assert!(!analyzer.is_enabled(&RevetConfig::default()));
+    let mut config = RevetConfig::default();
+    config.modules.debug_artifacts = true;
+    assert!(analyzer.is_enabled(&config));
+}
+
+// ── Debuggers ────────────────────────────────────────────────────
+
+#[test]
+fn test_debuggers_per_language() {
+    let cases = [
+        ("a.py", "import ipdb\nipdb.set_trace()\n", 2),
+        ("a.js", "function f() {\n  debugger\n}\n", 2),
+        (
+            "a.tsx",
+            "const A = () => {\n  debugger;\n  return <div />;\n};\n",
+            2,
+        ),
+        ("a.rb", "def f\n  binding.irb\nend\n", 2),
+        ("a.php", "<?php\nxdebug_break();\n", 2),
+        (
+            "a.go",
+            "package main\n\nfunc f() {\n\truntime.Breakpoint()\n}\n",
+            4,
+        ),
+        ("a.rs", "fn f(x: u8) -> u8 {\n    dbg!(x)\n}\n", 2),
+    ];
+    for (file, content, line) in cases {
+        assert_eq!(
+            lines(&analyze(file, content)),
+            vec![at(line, "debugger")],
+            "{}",
+            file
+        );
+    }
+}
+
+#[test]
+fn test_names_that_only_look_like_debuggers() {
+    let content = "\
+def breakpoint_report(debugger):
+    debugger.attach()
+    return breakpoint_report
+";
+    assert!(analyze("a.py", content).is_empty());
+    // A method named `dump` is not Symfony's dump()
+    assert!(analyze("a.php", "<?php\n$cache->dump($path);\n").is_empty());
+    // `debugger` as a Ruby variable, not a statement
+    assert!(analyze("a.rb", "debugger = Debugger.new\nstart(debugger)\n").is_empty());
+}
+
+#[test]
+fn test_unsupported_files_are_skipped() {
+    let analyzer = DebugArtifactsAnalyzer::new();
+    assert!(!analyzer.accepts(Path::new("Main.java")));
+    assert!(analyzer.accepts(Path::new("app/views.py")));
+    assert!(analyze("notes.txt", "breakpoint()\nprint(\"here\")\n").is_empty());
+}
+
+// ── Print debugging ──────────────────────────────────────────────
+
+#[test]
+fn test_placeholder_messages() {
+    let content = "\
+print(\"HERE!!\")
+print('--> got here 3')
+print(\"asdfgh\")
+print(\"test\", value)
+print(\"Loaded\", count, \"rows\")
+print(\"Testing the connection to\", host)
+print(\"\")
+print()
+";
+    assert_eq!(
+        lines(&analyze("a.py", content)),
+        vec![
+            at(1, "debug_print"),
+            at(2, "debug_print"),
+            at(3, "debug_print"),
+            at(4, "debug_print"),
+        ]
+    );
+}
+
+#[test]
+fn test_lone_values() {
+    let content = "\
+console.log(order);
+console.log(this.state);
+console.log(`${order}`);
+console.log(\"%o\", order);
+console.log(`order ${order.id} shipped`);
+console.log(order, cart);
+console.log(JSON.stringify(order));
+";
+    assert_eq!(
+        lines(&analyze("a.js", content)),
+        vec![
+            at(1, "debug_print"),
+            at(2, "debug_print"),
+            at(3, "debug_print"),
+            at(4, "debug_print"),
+        ]
+    );
+}
+
+#[test]
+fn test_format_strings() {
+    let rust = "\
+fn f(x: u8) {
+    println!(\"{x}\");
+    println!(\"{:?}\", x);
+    eprintln!(\"here {}\", x);
+    println!(\"{} of {}\", x, 10);
+    eprintln!(\"error: {}\", x);
+}
+";
+    assert_eq!(
+        lines(&analyze("a.rs", rust)),
+        vec![
+            at(2, "debug_print"),
+            at(3, "debug_print"),
+            at(4, "debug_print")
+        ]
+    );
+
+    let ruby = "puts \"#{total}\"\nputs \"Total: #{total}\"\npp rows\n";
+    assert_eq!(
+        lines(&analyze("a.rb", ruby)),
+        vec![at(1, "debug_print"), at(3, "debug_print")]
+    );
+}
+
+#[test]
+fn test_logging_is_never_a_print() {
+    let content = "\
+import logging
+log = logging.getLogger(__name__)
+log.debug(\"here\")
+log.info(user)
+sys.stdout.write(\"here\")
+";
+    assert!(analyze("a.py", content).is_empty());
+    assert!(analyze("a.js", "console.error(err);\nconsole.warn(\"here\");\n").is_empty());
+}
+
+#[test]
+fn test_custom_print_patterns() {
+    let config = DebugArtifactsConfig {
+        print_patterns: vec!["checkpoint".to_string(), "[(".to_string()],
+        ..DebugArtifactsConfig::default()
+    };
+    let content = "print(\"checkpoint 4\")\nprint(\"here\")\n";
+    assert_eq!(
+        lines(&analyze_with(&config, "a.py", content)),
+        vec![at(1, "debug_print")]
+    );
+}
+
+// ── Commented-out code ───────────────────────────────────────────
+
+#[test]
+fn test_commented_code_threshold() {
+    let content = "\
+def f(rows):
+    # total = 0
+    # for row in rows:
+    #     total += row.amount
+    # return total
+    return sum(r.amount for r in rows)
+";
+    assert!(analyze("a.py", content).is_empty());
+
+    let config = DebugArtifactsConfig {
+        comment_lines: 4,
+        ..DebugArtifactsConfig::default()
+    };
+    let findings = analyze_with(&config, "a.py", content);
+    assert_eq!(lines(&findings), vec![at(2, "commented_code")]);
+    assert_eq!(
+        findings[0].message,
+        "4 lines of commented-out code (lines 2–5)"
+    );
+}
+
+#[test]
+fn test_prose_comments_are_not_code() {
+    let content = "\
+// Copyright (c) 2024 Example Corp. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the \"License\");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at https://www.apache.org/licenses
+// Unless required by applicable law (or agreed to in writing), software
+// distributed under the License is distributed on an \"AS IS\" BASIS.
+package main
+";
+    assert!(analyze("a.go", content).is_empty());
+
+    let bullets = "\
+# Steps:
+# - load
+# - parse
+# - validate
+# - store
+# - report
+";
+    assert!(analyze("a.py", bullets).is_empty());
+}
+
+#[test]
+fn test_doc_comments_are_not_commented_code() {
+    let content = "\
+/// ```
+/// let cache = Cache::new(16);
+/// cache.insert(1, 2);
+/// cache.insert(3, 4);
+/// assert_eq!(cache.len(), 2);
+/// ```
+pub struct Cache;
+";
+    assert!(analyze("a.rs", content).is_empty());
+}
+
+#[test]
+fn test_commented_php_code() {
+    let content = "\
+<?php
+// $rows = $db->query($sql);
+// foreach ($rows as $row) {
+//     $total += $row['amount'];
+// }
+// return $total;
+return array_sum($amounts);
+";
+    assert_eq!(
+        lines(&analyze("a.php", content)),
+        vec![at(2, "commented_code")]
+    );
+}
+
+// ── Config ───────────────────────────────────────────────────────
+
+#[test]
+fn test_severity_overrides() {
+    let config: DebugArtifactsConfig =
+        toml::from_str("[severity]\ndebugger = \"warning\"\ndebug_print = \"info\"\n").unwrap();
+    let findings = analyze_with(&config, "a.py", "breakpoint()\nprint(\"here\")\n");
+    let severities: Vec<Severity> = findings.iter().map(|f| f.severity).collect();
+    assert_eq!(severities, vec![Severity::Warning, Severity::Info]);
+}
+
+#[test]
+fn test_sub_rule_selection() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(tmp.path().join("a.py"), "breakpoint()\nprint(\"here\")\n").unwrap();
+    let config: RevetConfig = toml::from_str(
+        "[modules]\ndebug_artifacts = true\n\n[analyzers.debug_artifacts]\ndisable = [\"debug_print\"]\n",
+    )
+    .unwrap();
+    let findings = common::run(tmp.path(), EXTENSIONS, &config);
+    let rules: Vec<_> = findings.iter().filter_map(|f| f.rule.as_deref()).collect();
+    assert_eq!(rules, vec!["debug_artifacts:debugger"]);
+}
+
+#[test]
+fn test_config_validation() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[analyzers.debug_artifacts]
+print_patterns = ["here", "(unclosed"]
+comment_lines = 1
+only = ["debuger"]
+
+[analyzers.debug_artifacts.severity]
+debugger = "fatal"
+prints = "info"
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    let errors: Vec<&String> = errors
+        .iter()
+        .filter(|e| e.contains("debug_artifacts"))
+        .collect();
+    assert_eq!(errors.len(), 5, "{:?}", errors);
+    assert!(errors.iter().any(|e| e.contains("\"(unclosed\"")));
+    assert!(errors.iter().any(|e| e.contains("comment_lines = 1")));
+    assert!(errors.iter().any(|e| e.contains("\"debuger\"")));
+    assert!(errors.iter().any(|e| e.contains("debugger = \"fatal\"")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("unknown sub-rule \"prints\"")));
+
+    assert!(RevetConfig::default()
+        .validate()
+        .0
+        .iter()
+        .all(|e| !e.contains("debug_artifacts")));
+}
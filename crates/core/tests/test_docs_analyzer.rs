@@ -0,0 +1,275 @@
+//! Tests for the documentation analyzer: the `docs` fixture, one file per
+//! language with documented, undocumented, private and trivial symbols, and
+//! its `[[project]]`s gated at 70%
+
+use revet_core::analyzer::docs::{self, DocsAnalyzer};
+use revet_core::analyzer::GraphAnalyzer;
+use revet_core::config::RevetConfig;
+use revet_core::discovery::discover_files;
+use revet_core::graph::{CodeGraph, Doc, Node, NodeData, NodeKind};
+use revet_core::{ParserDispatcher, Severity};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures/docs")
+        .canonicalize()
+        .unwrap()
+}
+
+fn config() -> RevetConfig {
+    RevetConfig::from_file(&root().join(".revet.toml")).unwrap()
+}
+
+fn graph(config: &RevetConfig) -> CodeGraph {
+    let root = root();
+    let dispatcher = ParserDispatcher::new_with_config(config);
+    let files = discover_files(&root, &dispatcher.supported_extensions(), &[]).unwrap();
+    let (graph, errors) = dispatcher.parse_files_parallel(&files, root);
+    assert!(errors.is_empty(), "{:?}", errors);
+    graph
+}
+
+/// Counted symbols per file: `(documented, public)`, and the undocumented
+/// ones by name
+fn counts(config: &RevetConfig) -> BTreeMap<String, ((usize, usize), Vec<String>)> {
+    let graph = graph(config);
+    let survey = docs::survey(&graph, config);
+    let mut counts: BTreeMap<String, ((usize, usize), Vec<String>)> = survey
+        .coverage
+        .files
+        .iter()
+        .map(|f| {
+            let c = f.coverage;
+            (f.path.clone(), ((c.documented, c.public), Vec::new()))
+        })
+        .collect();
+    for symbol in survey.symbols.iter().filter(|s| !s.documented) {
+        let node = graph.node(symbol.node).unwrap();
+        let path = node.file_path().strip_prefix(root()).unwrap();
+        let file = path.to_string_lossy().replace('\\', "/");
+        counts
+            .get_mut(&file)
+            .unwrap()
+            .1
+            .push(node.name().to_string());
+    }
+    counts
+}
+
+#[test]
+fn test_public_symbols_per_language() {
+    let counts = counts(&config());
+    let expected: &[(&str, (usize, usize), &[&str])] = &[
+        // `helper` is not in `__all__`; `__init__` and `_private` are
+        // private; `diameter` is a property
+        ("python/shapes.py", (3, 5), &["perimeter", "describe"]),
+        // `helper` is private; `MAX_SIDES` is a constant, `width` an
+        // accessor and `is_square` a getter
+        ("rust/lib.rs", (4, 5), &["perimeter"]),
+        // `GetWidth` is a getter; `//go:noinline` is no documentation
+        ("go/geometry.go", (3, 5), &["Perimeter", "Rect.Diagonal"]),
+        // `check` is private; `MAX_SIDES` is a constant
+        ("web/shapes.ts", (3, 4), &["perimeter"]),
+        // `Shape` is package-private; `getScale` is a getter
+        ("java/Geometry.java", (3, 4), &["Geometry.diagonal"]),
+        (
+            "cs/Geometry.cs",
+            (3, 5),
+            &["Shapes.Perimeter", "IShape.Area"],
+        ),
+    ];
+    for (file, coverage, undocumented) in expected {
+        let (found, names) = &counts[*file];
+        assert_eq!(found, coverage, "{}", file);
+        assert_eq!(names, undocumented, "{}", file);
+    }
+    // Kotlin's parser records no doc comments, and tests are left out
+    assert_eq!(counts.len(), expected.len(), "{:?}", counts.keys());
+}
+
+#[test]
+fn test_doc_comments_record_their_first_line() {
+    let graph = graph(&config());
+    let doc = |file: &str, name: &str| {
+        let ids = graph.find_nodes(&root().join(file), Some(name));
+        graph.node(ids[0]).unwrap().doc().clone()
+    };
+    let present = |line: &str| Doc::Present(line.to_string());
+    assert_eq!(doc("rust/lib.rs", "area"), present("Area of a rectangle."));
+    assert_eq!(doc("rust/lib.rs", "Rect"), present("A rectangle."));
+    assert_eq!(
+        doc("rust/lib.rs", "Rect.square"),
+        present("Square of side `side`.")
+    );
+    assert_eq!(doc("rust/lib.rs", "perimeter"), Doc::Missing);
+    assert_eq!(
+        doc("python/shapes.py", "area"),
+        present("Area of a circle of `radius`.")
+    );
+    assert_eq!(doc("python/shapes.py", "Circle"), present("A circle."));
+    assert_eq!(
+        doc("go/geometry.go", "Rect.Scale"),
+        present("Scale returns the rectangle scaled by f.")
+    );
+    assert_eq!(doc("go/geometry.go", "Rect.Diagonal"), Doc::Missing);
+    assert_eq!(
+        doc("web/shapes.ts", "area"),
+        present("Area of a rectangle.")
+    );
+    assert_eq!(
+        doc("java/Geometry.java", "Geometry.perimeter"),
+        present("Perimeter of a rectangle.")
+    );
+    assert_eq!(
+        doc("cs/Geometry.cs", "Shapes"),
+        present("Geometry helpers.")
+    );
+    assert_eq!(
+        doc("cs/Geometry.cs", "Shapes.Area"),
+        present("Area of a rectangle.")
+    );
+    assert_eq!(doc("kotlin/Shapes.kt", "area"), Doc::Unrecorded);
+}
+
+#[test]
+fn test_coverage_per_project_and_gate() {
+    let config = config();
+    let graph = graph(&config);
+    let coverage = docs::coverage(&graph, &config);
+    assert_eq!((coverage.total.documented, coverage.total.public), (19, 28));
+    assert!((coverage.total.percent - 19.0 * 100.0 / 28.0).abs() < 1e-9);
+    let projects: Vec<(&str, f64)> = coverage
+        .projects
+        .iter()
+        .map(|p| (p.path.as_str(), p.coverage.percent))
+        .collect();
+    assert_eq!(
+        projects,
+        [
+            ("cs", 60.0),
+            ("go", 60.0),
+            ("java", 75.0),
+            ("python", 60.0),
+            ("rust", 80.0),
+            ("web", 75.0),
+        ]
+    );
+    // Least covered files first
+    assert_eq!(coverage.files[0].coverage.percent, 60.0);
+    assert_eq!(coverage.files.last().unwrap().path, "rust/lib.rs");
+
+    let findings = DocsAnalyzer::new().analyze_graph(&graph, &config);
+    let undocumented = findings
+        .iter()
+        .filter(|f| f.rule.as_deref() == Some("undocumented"))
+        .inspect(|f| assert_eq!(f.severity, Severity::Info))
+        .count();
+    assert_eq!(undocumented, 28 - 19);
+    let gated: Vec<&Path> = findings
+        .iter()
+        .filter(|f| f.rule.as_deref() == Some("coverage"))
+        .inspect(|f| assert_eq!(f.severity, Severity::Error))
+        .map(|f| f.file.strip_prefix(root()).unwrap())
+        .collect();
+    assert_eq!(
+        gated,
+        [Path::new("cs"), Path::new("go"), Path::new("python")]
+    );
+    let go = findings
+        .iter()
+        .find(|f| f.rule.as_deref() == Some("coverage") && f.file.ends_with("go"))
+        .unwrap();
+    assert_eq!(
+        go.message,
+        "Documentation coverage of `go` is 60.0% (3 of 5 public symbols), below the minimum of 70%"
+    );
+
+    // Without a minimum there is no gate; without projects the repository
+    // is one
+    let mut ungated = config.clone();
+    ungated.analyzers.docs.min_coverage = None;
+    let findings = DocsAnalyzer::new().analyze_graph(&graph, &ungated);
+    assert!(findings
+        .iter()
+        .all(|f| f.rule.as_deref() == Some("undocumented")));
+    let mut whole = config.clone();
+    whole.projects.clear();
+    let coverage = docs::coverage(&graph, &whole);
+    assert_eq!(coverage.projects.len(), 1);
+    assert_eq!(coverage.projects[0].path, ".");
+    assert_eq!(coverage.projects[0].coverage, coverage.total);
+    let findings = DocsAnalyzer::new().analyze_graph(&graph, &whole);
+    let gate = findings
+        .iter()
+        .find(|f| f.rule.as_deref() == Some("coverage"))
+        .unwrap();
+    assert_eq!(gate.file, PathBuf::new());
+}
+
+#[test]
+fn test_trivial_symbol_heuristics_are_configurable() {
+    let mut getters = config();
+    getters.analyzers.docs.skip_getters = false;
+    let found = counts(&getters);
+    assert_eq!(
+        found["rust/lib.rs"].1,
+        ["perimeter", "Rect.width", "Rect.is_square"]
+    );
+    assert_eq!(found["python/shapes.py"].0, (3, 6));
+    assert_eq!(found["go/geometry.go"].0, (3, 6));
+    assert_eq!(found["java/Geometry.java"].0, (3, 5));
+
+    let mut constants = config();
+    constants.analyzers.docs.skip_constants = false;
+    let found = counts(&constants);
+    assert_eq!(found["rust/lib.rs"].1, ["perimeter", "MAX_SIDES"]);
+    assert_eq!(found["web/shapes.ts"].1, ["perimeter", "MAX_SIDES"]);
+}
+
+#[test]
+fn test_unrecorded_and_private_nodes_are_not_counted() {
+    let config = config();
+    let mut graph = CodeGraph::new(root());
+    let file = root().join("web/extra.ts");
+    graph.add_node(Node::new(
+        NodeKind::File,
+        "extra.ts".to_string(),
+        file.clone(),
+        0,
+        NodeData::File {
+            language: "typescript".to_string(),
+            code_kind: Default::default(),
+        },
+    ));
+    let function = |name: &str, line| {
+        Node::new(
+            NodeKind::Function,
+            name.to_string(),
+            file.clone(),
+            line,
+            NodeData::Function {
+                parameters: vec![],
+                return_type: None,
+            },
+        )
+    };
+    // A node cached before its parser recorded docs
+    graph.add_node(function("stale", 1));
+    let mut missing = function("fresh", 2);
+    missing.set_doc(Doc::Missing);
+    graph.add_node(missing);
+
+    // The file doesn't exist, so nothing declares either exported; as
+    // unsaved content it does
+    assert!(docs::survey(&graph, &config).symbols.is_empty());
+    let content = "export function stale() {}\nexport function fresh() {}\n";
+    let findings = DocsAnalyzer::new().analyze_file(&graph, &file, content, &config);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].line, 2);
+    assert_eq!(
+        findings[0].message,
+        "Public symbol `fresh` has no documentation comment"
+    );
+}
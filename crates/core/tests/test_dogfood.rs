@@ -295,7 +295,7 @@ fn collect_rs_files(dir: &PathBuf, out: &mut Vec<PathBuf>) {
             let path = entry.path();
             if path.is_dir() {
                 collect_rs_files(&path, out);
-            } else if path.extension().map_or(false, |ext| ext == "rs") {
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
                 out.push(path);
             }
         }
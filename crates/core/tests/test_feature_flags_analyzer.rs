@@ -0,0 +1,442 @@
+//! Integration tests for FeatureFlagsAnalyzer
+
+mod common;
+
+use revet_core::analyzer::feature_flags::inventory::{Inventory, State};
+use revet_core::analyzer::feature_flags::FeatureFlagsAnalyzer;
+use revet_core::analyzer::Analyzer;
+use revet_core::config::{FeatureFlagsConfig, RevetConfig};
+use revet_core::finding::{Finding, Severity};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const FIXTURE: &str = "feature_flags";
+const EXTENSIONS: &[&str] = &[".py", ".ts", ".js", ".go", ".java"];
+
+/// (repo-relative file, line, sub-rule) for each FLAG finding, sorted
+fn summary(findings: &[Finding], root: &Path) -> Vec<(String, usize, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("FLAG"))
+        .map(|f| {
+            let file = f.file.strip_prefix(root).unwrap_or(&f.file);
+            (
+                file.to_string_lossy().replace('\\', "/"),
+                f.line,
+                f.rule.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+fn row(file: &str, line: usize, rule: &str) -> (String, usize, String) {
+    (file.to_string(), line, rule.to_string())
+}
+
+fn write(root: &Path, rel: &str, content: &str) {
+    let path = root.join(rel);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+fn analyze(root: &Path) -> Vec<Finding> {
+    analyze_with(&FeatureFlagsConfig::default(), root)
+}
+
+fn analyze_with(config: &FeatureFlagsConfig, root: &Path) -> Vec<Finding> {
+    FeatureFlagsAnalyzer::with_config(config)
+        .analyze_files(&common::discover(root, EXTENSIONS), root)
+}
+
+/// (sub-rule, message) pairs, sorted
+fn messages(findings: &[Finding]) -> Vec<(String, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .map(|f| (f.rule.clone().unwrap_or_default(), f.message.clone()))
+        .collect();
+    rows.sort();
+    rows
+}
+
+fn inventory(root: &Path, entries: &[(&str, &str)]) {
+    let content: String = entries
+        .iter()
+        .map(|(name, state)| format!("[[flag]]\nname = \"{}\"\nstate = \"{}\"\n\n", name, state))
+        .collect();
+    write(root, ".revet/flags.toml", &content);
+}
+
+// ── Fixture ──────────────────────────────────────────────────────
+
+#[test]
+fn test_fixture() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    assert_eq!(
+        summary(&findings, &root),
+        vec![
+            row(".revet/flags.toml", 29, "feature_flags:unused_flag"),
+            row("app/checkout.py", 9, "feature_flags:stale_flag"),
+            row("app/checkout.py", 11, "feature_flags:dead_branch"),
+            row("app/checkout.py", 22, "feature_flags:unknown_flag"),
+            row("services/billing.go", 6, "feature_flags:stale_flag"),
+            row(
+                "src/main/java/com/example/search/Search.java",
+                5,
+                "feature_flags:unknown_flag"
+            ),
+            row("web/nav.ts", 4, "feature_flags:dead_branch"),
+            row("web/nav.ts", 4, "feature_flags:stale_flag"),
+            row("web/nav.ts", 8, "feature_flags:dead_branch"),
+            row("web/nav.ts", 8, "feature_flags:stale_flag"),
+            row("web/nav.ts", 12, "feature_flags:dynamic_flag"),
+        ]
+    );
+}
+
+#[test]
+fn test_fixture_messages_and_severities() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let at = |file: &str, line: usize, rule: &str| {
+        findings
+            .iter()
+            .find(|f| {
+                f.file == root.join(file)
+                    && f.line == line
+                    && f.rule.as_deref() == Some(&format!("feature_flags:{}", rule))
+            })
+            .unwrap()
+    };
+
+    let stale = at("app/checkout.py", 9, "stale_flag");
+    assert_eq!(
+        stale.message,
+        "Flag `checkout-v2` was launched 2024-03-01; remove the check and the dead branch"
+    );
+    assert_eq!(stale.severity, Severity::Warning);
+    assert_eq!(
+        at("web/nav.ts", 4, "stale_flag").message,
+        "Flag `dark-mode` is launched; remove the check and the dead branch"
+    );
+    assert_eq!(
+        at("web/nav.ts", 8, "stale_flag").message,
+        "Flag `old-nav` was retired 2023-10-12; remove the check and the code it guards"
+    );
+
+    let dead = at("app/checkout.py", 11, "dead_branch");
+    assert_eq!(
+        dead.message,
+        "Lines 11–12 only run while launched flag `checkout-v2` is off"
+    );
+    assert_eq!(dead.severity, Severity::Info);
+    assert_eq!(
+        at("web/nav.ts", 4, "dead_branch").message,
+        "Lines 4–6 only run while launched flag `dark-mode` is off"
+    );
+    assert_eq!(
+        at("web/nav.ts", 8, "dead_branch").message,
+        "Lines 8–10 only run while retired flag `old-nav` is on"
+    );
+
+    let unknown = at("app/checkout.py", 22, "unknown_flag");
+    assert_eq!(
+        unknown.message,
+        "Flag `beta-expotr` is not in the flag inventory (.revet/flags.toml)"
+    );
+    assert_eq!(unknown.severity, Severity::Info);
+    assert_eq!(
+        at(".revet/flags.toml", 29, "unused_flag").message,
+        "Flag `beta-export` is in the inventory but no code checks it"
+    );
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let root = common::fixture_root(FIXTURE);
+    let findings = common::run(&root, EXTENSIONS, &RevetConfig::default());
+    assert!(findings.iter().all(|f| !f.id.starts_with("FLAG")));
+}
+
+// ── State transitions ────────────────────────────────────────────
+
+#[test]
+fn test_state_transitions() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "app.py",
+        "def pay(user):\n    if ld.variation(\"checkout-v2\", user, False):\n        return new()\n    else:\n        return old()\n",
+    );
+    let rules = |state: Option<&str>| {
+        let entries: Vec<(&str, &str)> = state.map(|s| ("checkout-v2", s)).into_iter().collect();
+        inventory(dir.path(), &entries);
+        let mut rules: Vec<(String, usize)> = analyze(dir.path())
+            .iter()
+            .map(|f| (f.rule.clone().unwrap_or_default(), f.line))
+            .collect();
+        rules.sort();
+        rules
+    };
+    let rule = |name: &str, line: usize| (name.to_string(), line);
+
+    // Rolling out: nothing to clean up
+    assert_eq!(rules(Some("active")), vec![]);
+    // On for everyone: the else branch is dead
+    assert_eq!(
+        rules(Some("launched")),
+        vec![rule("dead_branch", 4), rule("stale_flag", 2)]
+    );
+    // Off for everyone: the guarded body is dead
+    assert_eq!(
+        rules(Some("retired")),
+        vec![rule("dead_branch", 3), rule("stale_flag", 2)]
+    );
+    // Dropped from the inventory while still checked
+    assert_eq!(rules(None), vec![rule("unknown_flag", 2)]);
+}
+
+#[test]
+fn test_unused_entries_by_state() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "app.py", "x = 1\n");
+    inventory(
+        dir.path(),
+        &[("a", "active"), ("b", "launched"), ("c", "retired")],
+    );
+    assert_eq!(
+        messages(&analyze(dir.path())),
+        vec![
+            (
+                "unused_flag".to_string(),
+                "Flag `a` is in the inventory but no code checks it".to_string()
+            ),
+            (
+                "unused_flag".to_string(),
+                "Flag `b` is in the inventory but no code checks it".to_string()
+            ),
+        ]
+    );
+}
+
+// ── Checks ───────────────────────────────────────────────────────
+
+#[test]
+fn test_dynamic_names_are_unverifiable_and_cover_their_prefix() {
+    let dir = TempDir::new().unwrap();
+    inventory(
+        dir.path(),
+        &[("checkout-eu", "launched"), ("checkout-us", "active")],
+    );
+    write(
+        dir.path(),
+        "app.js",
+        "export const a = (region) => client.variation('checkout-' + region, user, false);\nexport const b = (name) => client.variation(name, user, false);\n",
+    );
+    assert_eq!(
+        messages(&analyze(dir.path())),
+        vec![
+            (
+                "dynamic_flag".to_string(),
+                "Flag name `'checkout-' + region` is built at runtime and cannot be checked against the inventory"
+                    .to_string()
+            ),
+            (
+                "dynamic_flag".to_string(),
+                "Flag name `name` is built at runtime and cannot be checked against the inventory"
+                    .to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_sdk_tables_and_custom_wrappers() {
+    let dir = TempDir::new().unwrap();
+    inventory(dir.path(), &[("old", "retired")]);
+    write(
+        dir.path(),
+        "a.py",
+        "unleash_client.is_enabled(\"old\")\nclient.get_boolean_value(\"old\", False)\nis_feature_on(\"old\")\n",
+    );
+    write(
+        dir.path(),
+        "b.go",
+        "package b\n\nfunc f() {\n\tunleash.IsEnabled(\"old\")\n\tclient.IsEnabled(\"old\")\n}\n",
+    );
+    write(
+        dir.path(),
+        "C.java",
+        "class C {\n    void f() {\n        unleash.isEnabled(\"old\");\n        client.getBooleanValue(\"old\", false);\n    }\n}\n",
+    );
+    let stale_lines = |findings: Vec<Finding>| {
+        let mut rows: Vec<(String, usize)> = findings
+            .iter()
+            .map(|f| {
+                let name = f.file.file_name().unwrap().to_string_lossy().to_string();
+                (name, f.line)
+            })
+            .collect();
+        rows.sort();
+        rows
+    };
+    let row = |file: &str, line: usize| (file.to_string(), line);
+
+    assert_eq!(
+        stale_lines(analyze(dir.path())),
+        vec![
+            row("C.java", 3),
+            row("C.java", 4),
+            row("a.py", 1),
+            row("a.py", 2),
+            row("b.go", 4),
+        ]
+    );
+
+    let config: RevetConfig = toml::from_str(
+        "[analyzers.feature_flags]\nsdks = [\"unleash\"]\nfunctions = [\"is_feature_on\"]\n",
+    )
+    .unwrap();
+    assert_eq!(
+        stale_lines(analyze_with(&config.analyzers.feature_flags, dir.path())),
+        vec![
+            row("C.java", 3),
+            row("a.py", 1),
+            row("a.py", 3),
+            row("b.go", 4)
+        ]
+    );
+}
+
+#[test]
+fn test_guards_through_negation_and_elif() {
+    let dir = TempDir::new().unwrap();
+    inventory(dir.path(), &[("new-ui", "launched")]);
+    write(
+        dir.path(),
+        "app.py",
+        "def f(u):\n    if not (ld.variation(\"new-ui\", u, False)):\n        old()\n    if ld.variation(\"new-ui\", u, False):\n        new()\n    elif u.admin:\n        admin()\n    else:\n        old()\n    if ld.variation(\"new-ui\", u, False) and u.beta:\n        beta()\n",
+    );
+    let dead: Vec<String> = analyze(dir.path())
+        .iter()
+        .filter(|f| f.rule.as_deref() == Some("dead_branch"))
+        .map(|f| f.message.clone())
+        .collect();
+    assert_eq!(
+        dead,
+        vec![
+            "Lines 3–3 only run while launched flag `new-ui` is off",
+            "Lines 6–9 only run while launched flag `new-ui` is off",
+        ]
+    );
+}
+
+#[test]
+fn test_selection_turns_off_dead_branches() {
+    let dir = TempDir::new().unwrap();
+    inventory(dir.path(), &[("new-ui", "launched")]);
+    write(
+        dir.path(),
+        "app.py",
+        "if ld.variation(\"new-ui\", u, False):\n    new()\nelse:\n    old()\n",
+    );
+    let mut config: RevetConfig =
+        toml::from_str("[analyzers.feature_flags]\ndisable = [\"dead_branch\"]\n").unwrap();
+    config.modules.feature_flags = true;
+    let findings = common::run(dir.path(), EXTENSIONS, &config);
+    assert_eq!(
+        summary(&findings, dir.path()),
+        vec![row("app.py", 1, "feature_flags:stale_flag")]
+    );
+}
+
+// ── Inventory ────────────────────────────────────────────────────
+
+#[test]
+fn test_no_inventory_reports_nothing() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "app.py",
+        "ld.variation(\"anything\", u, False)\nld.variation(name, u, False)\n",
+    );
+    assert!(analyze(dir.path()).is_empty());
+}
+
+#[test]
+fn test_inventory_dates_and_fields() {
+    let inventory = Inventory::parse(
+        PathBuf::from("flags.toml"),
+        "[[flag]]\nname = \"a\"\nstate = \"launched\"\nowner = \"web\"\ncreated = 2023-11-02\nsince = \"2024-03-01\"\n\n[[flag]]\nname = \"b\"\nstate = \"active\"\n",
+    )
+    .unwrap();
+    let a = inventory.get("a").unwrap();
+    assert_eq!(a.state, State::Launched);
+    assert_eq!(a.owner.as_deref(), Some("web"));
+    assert_eq!(a.created.as_deref(), Some("2023-11-02"));
+    assert_eq!(a.since.as_deref(), Some("2024-03-01"));
+    assert_eq!(a.line, 2);
+    assert_eq!(inventory.get("b").unwrap().line, 9);
+}
+
+#[test]
+fn test_invalid_inventory_is_reported_alone() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        ".revet/flags.toml",
+        "[[flag]]\nname = \"a\"\nstate = \"active\"\n\n[[flag]]\nname = \"a\"\nstate = \"shipped\"\n",
+    );
+    write(
+        dir.path(),
+        "app.py",
+        "ld.variation(\"unknown\", u, False)\n",
+    );
+    let findings = analyze(dir.path());
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].rule.as_deref(), Some("invalid_inventory"));
+    assert_eq!(findings[0].line, 7);
+    assert!(
+        findings[0].message.contains("shipped"),
+        "{}",
+        findings[0].message
+    );
+
+    write(
+        dir.path(),
+        ".revet/flags.toml",
+        "[[flag]]\nname = \"a\"\nstate = \"active\"\n\n[[flag]]\nname = \"a\"\nstate = \"retired\"\n",
+    );
+    let findings = analyze(dir.path());
+    assert_eq!(
+        messages(&findings),
+        vec![(
+            "invalid_inventory".to_string(),
+            "Flag inventory cannot be read: flag \"a\" is listed twice".to_string()
+        )]
+    );
+    assert_eq!(findings[0].line, 6);
+}
+
+#[test]
+fn test_inventory_path_and_sdk_validation() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "flags/inventory.toml",
+        "[[flag]]\nname = \"gone\"\nstate = \"active\"\n",
+    );
+    let config: RevetConfig = toml::from_str(
+        "[analyzers.feature_flags]\ninventory = \"flags/inventory.toml\"\nsdks = [\"split\"]\n",
+    )
+    .unwrap();
+    let findings = analyze_with(&config.analyzers.feature_flags, dir.path());
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].file, dir.path().join("flags/inventory.toml"));
+
+    let (errors, _) = config.validate();
+    assert!(errors.iter().any(|e| e
+        == "[analyzers.feature_flags] sdk \"split\" is unknown. Must be one of: launchdarkly, unleash, openfeature"));
+    let (errors, _) = common::fixture_config(FIXTURE).validate();
+    assert!(errors.is_empty(), "{:?}", errors);
+}
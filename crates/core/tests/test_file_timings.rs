@@ -0,0 +1,139 @@
+//! Integration tests for per-file analyzer timing (`--timings=full`,
+//! `[performance] slow_file_warn_ms`)
+
+use revet_core::analyzer::file_timing::{self, SLOWEST_FILES};
+use revet_core::config::RevetConfig;
+use revet_core::{AnalyzerDispatcher, AnalyzerTiming, Severity, TimingReport};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// `n` Python files, each with a SQL injection and a hardcoded secret
+fn repo(n: usize) -> (TempDir, Vec<PathBuf>) {
+    let dir = TempDir::new().unwrap();
+    let files = (0..n)
+        .map(|i| {
+            let path = dir.path().join(format!("m{:02}.py", i));
+            let body = format!(
+                "password = \"hunter2-Secret-Value\"\n{}",
+                "cursor.execute(f\"SELECT * FROM t WHERE id = {uid}\")\n".repeat(i + 1)
+            );
+            std::fs::write(&path, body).unwrap();
+            path
+        })
+        .collect();
+    (dir, files)
+}
+
+fn timing<'a>(timings: &'a [AnalyzerTiming], prefix: &str) -> &'a AnalyzerTiming {
+    timings.iter().find(|t| t.prefix == prefix).unwrap()
+}
+
+#[test]
+fn test_time_file_without_a_clock_only_runs() {
+    assert_eq!(file_timing::time_file(Path::new("a.py"), || 7), 7);
+}
+
+#[test]
+fn test_file_timings_are_off_by_default() {
+    let (dir, files) = repo(3);
+    let config = RevetConfig::default();
+    let (_, timings) = AnalyzerDispatcher::new_with_config(&config).run_all_parallel_timed(
+        &files,
+        dir.path(),
+        &config,
+    );
+    assert!(timings.iter().all(|t| t.files.is_empty()));
+    assert!(timings.iter().all(|t| t.peak_alloc.is_none()));
+}
+
+#[test]
+fn test_file_timings_keep_the_slowest_files() {
+    let (dir, files) = repo(SLOWEST_FILES + 4);
+    let config = RevetConfig::default();
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config).with_file_timings(true);
+    let (off_findings, _) = AnalyzerDispatcher::new_with_config(&config).run_all_parallel_timed(
+        &files,
+        dir.path(),
+        &config,
+    );
+    let (findings, timings) = dispatcher.run_all_parallel_timed(&files, dir.path(), &config);
+    // Timing per file does not change the findings
+    assert_eq!(findings.len(), off_findings.len());
+
+    // SQL is per-file (timed by the dispatcher), secrets use the helper
+    for prefix in ["SQL", "SEC"] {
+        let t = timing(&timings, prefix);
+        assert_eq!(t.files.len(), SLOWEST_FILES, "{}", prefix);
+        assert!(t.files.windows(2).all(|w| w[0].duration >= w[1].duration));
+        assert!(t.files.iter().all(|f| files.contains(&f.file)));
+    }
+}
+
+#[test]
+fn test_partitioned_run_times_files_too() {
+    let (dir, files) = repo(4);
+    let config = RevetConfig::default();
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config).with_file_timings(true);
+    let partitions = revet_core::analyzer::partition::partition_by(&files, dir.path(), 2);
+    let (_, timings) =
+        dispatcher.run_all_partitioned_timed(&files, &partitions, dir.path(), &config, |_, _| {});
+    let mut timed: Vec<&PathBuf> = timing(&timings, "SQL")
+        .files
+        .iter()
+        .map(|f| &f.file)
+        .collect();
+    timed.sort();
+    assert_eq!(timed, files.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_slow_file_threshold_reports_info_findings() {
+    let (dir, files) = repo(3);
+    let mut config = RevetConfig::default();
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config);
+    let (_, timings) = dispatcher.run_all_parallel_timed(&files, dir.path(), &config);
+    assert!(dispatcher.run_slow_files(&timings, &config).is_none());
+
+    // Every file takes at least 0ms: one finding per file
+    config.performance.slow_file_warn_ms = Some(0);
+    let (_, timings) = dispatcher.run_all_parallel_timed(&files, dir.path(), &config);
+    let slow = dispatcher.run_slow_files(&timings, &config).unwrap();
+    assert_eq!(slow.len(), 3);
+    assert_eq!(slow[0].id, "PERF-001");
+    assert!(slow.iter().all(|f| f.severity == Severity::Info));
+    assert!(slow[0].message.starts_with("Slow to analyze: "));
+    assert!(slow[0].message.ends_with("(threshold 0 ms)"));
+    let mut flagged: Vec<&PathBuf> = slow.iter().map(|f| &f.file).collect();
+    flagged.sort();
+    assert_eq!(flagged, files.iter().collect::<Vec<_>>());
+
+    // Far above anything a test file takes
+    config.performance.slow_file_warn_ms = Some(600_000);
+    let (_, timings) = dispatcher.run_all_parallel_timed(&files, dir.path(), &config);
+    assert!(dispatcher
+        .run_slow_files(&timings, &config)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_timing_report_lists_files_relative_to_the_root() {
+    let (dir, files) = repo(2);
+    let config = RevetConfig::default();
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config).with_file_timings(true);
+    let (_, timings) = dispatcher.run_all_parallel_timed(&files, dir.path(), &config);
+
+    let full = TimingReport::new(&timings, dir.path(), true);
+    let sql = full.analyzers.iter().find(|a| a.prefix == "SQL").unwrap();
+    assert_eq!(sql.findings, 3);
+    let mut names: Vec<&str> = sql.slowest_files.iter().map(|f| f.file.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec!["m00.py", "m01.py"]);
+    let total: f64 = full.analyzers.iter().map(|a| a.ms).sum();
+    assert!((full.total_ms - total).abs() < 1e-9);
+
+    let summary = TimingReport::new(&timings, dir.path(), false);
+    assert!(summary.analyzers.iter().all(|a| a.slowest_files.is_empty()));
+    let json = serde_json::to_value(&summary).unwrap();
+    assert!(json["analyzers"][0].get("slowest_files").is_none());
+}
@@ -0,0 +1,297 @@
+//! Tests for stable node identity across runs (`NodeFingerprint`)
+
+use std::path::{Path, PathBuf};
+
+use proptest::prelude::*;
+use revet_core::config::RevetConfig;
+use revet_core::graph::{Node, NodeData, NodeKind, Parameter};
+use revet_core::store::{reconstruct_graph, GraphStore, MemoryStore, StoreNodeId};
+use revet_core::{CodeGraph, NodeFingerprint, ParserDispatcher};
+use tempfile::TempDir;
+
+#[cfg(feature = "cozo-store")]
+use revet_core::store::CozoStore;
+
+const SOURCE: &[&str] = &[
+    "import os",
+    "class Orders:",
+    "    def place(self, item: str, qty: int = 1) -> bool:",
+    "        return True",
+    "    def cancel(self, order_id: int) -> None:",
+    "        pass",
+    "def place(item: str) -> bool:",
+    "    return Orders().place(item)",
+    "LIMIT: int = 10",
+];
+
+/// `(kind, name, fingerprint)` of every non-file node of `lines` parsed as
+/// `src/orders.py` in a fresh root, sorted by fingerprint
+fn fingerprints(lines: &[String]) -> Vec<(NodeKind, String, NodeFingerprint)> {
+    let dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    let file = dir.path().join("src/orders.py");
+    std::fs::write(&file, lines.join("\n")).unwrap();
+    let dispatcher = ParserDispatcher::new_with_config(&RevetConfig::default());
+    let (graph, errors) = dispatcher.parse_files_parallel(&[file], dir.path().to_path_buf());
+    assert!(errors.is_empty(), "errors: {:?}", errors);
+    let mut out: Vec<_> = graph
+        .nodes()
+        .filter(|(_, n)| n.kind() != &NodeKind::File)
+        .map(|(_, n)| {
+            (
+                *n.kind(),
+                n.name().to_string(),
+                *n.fingerprint().expect("parsed nodes are fingerprinted"),
+            )
+        })
+        .collect();
+    out.sort_by_key(|(_, _, f)| *f);
+    out
+}
+
+fn source() -> Vec<String> {
+    SOURCE.iter().map(|l| l.to_string()).collect()
+}
+
+/// `SOURCE` with blank or comment lines inserted before the lines picked
+/// by `inserts` (index, comment?), and trailing spaces on others
+fn edited(inserts: &[(usize, bool)], trailing: &[usize]) -> Vec<String> {
+    let mut lines = source();
+    for &i in trailing {
+        let i = i % lines.len();
+        lines[i].push_str("   ");
+    }
+    let mut inserts = inserts.to_vec();
+    inserts.sort_by_key(|(i, _)| std::cmp::Reverse(*i));
+    for (i, comment) in inserts {
+        let i = i % (lines.len() + 1);
+        // Indent like the line it precedes, so the block structure holds
+        let indent: String = lines
+            .get(i)
+            .map(|l| l.chars().take_while(|c| *c == ' ').collect())
+            .unwrap_or_default();
+        let line = if comment {
+            format!("{}# note", indent)
+        } else {
+            String::new()
+        };
+        lines.insert(i, line);
+    }
+    lines
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(24))]
+
+    #[test]
+    fn prop_stable_under_whitespace_and_comment_edits(
+        inserts in prop::collection::vec((0usize..10, any::<bool>()), 0..6),
+        trailing in prop::collection::vec(0usize..9, 0..4),
+    ) {
+        prop_assert_eq!(fingerprints(&edited(&inserts, &trailing)), fingerprints(&source()));
+    }
+
+    #[test]
+    fn prop_changes_under_signature_edits(param in "[a-z]{1,8}", ty in "(int|str|float|bytes)") {
+        let mut lines = source();
+        lines[6] = format!("def place(item: str, {}: {}) -> bool:", param, ty);
+        let before = fingerprints(&source());
+        let after = fingerprints(&lines);
+
+        // Only the edited function's fingerprint changes, within its symbol
+        let gone: Vec<_> = before.iter().filter(|e| !after.contains(e)).collect();
+        let new: Vec<_> = after.iter().filter(|e| !before.contains(e)).collect();
+        prop_assert_eq!(gone.len(), 1);
+        prop_assert_eq!(new.len(), 1);
+        prop_assert_eq!(gone[0].1.as_str(), "place");
+        prop_assert_eq!(new[0].1.as_str(), "place");
+        prop_assert!(gone[0].2.same_symbol(&new[0].2));
+    }
+}
+
+#[test]
+fn test_method_and_function_of_one_name_differ() {
+    let fps = fingerprints(&source());
+    let places: Vec<_> = fps.iter().filter(|(_, n, _)| n == "place").collect();
+    assert_eq!(places.len(), 2);
+    assert!(!places[0].2.same_symbol(&places[1].2));
+}
+
+#[test]
+fn test_duplicates_get_ordinals() {
+    let lines: Vec<String> = ["def f(a):", "    pass", "def f(a):", "    pass"]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+    let fps = fingerprints(&lines);
+    assert_eq!(fps.len(), 2);
+    assert!(fps[0].2.same_symbol(&fps[1].2));
+    assert_eq!((fps[0].2.ordinal(), fps[1].2.ordinal()), (0, 1));
+}
+
+#[test]
+fn test_display_round_trip() {
+    for (_, _, fingerprint) in fingerprints(&source()) {
+        let text = fingerprint.to_string();
+        assert_eq!(text.parse::<NodeFingerprint>().unwrap(), fingerprint);
+    }
+    assert!("abc".parse::<NodeFingerprint>().is_err());
+    assert!("0:0:0:0".parse::<NodeFingerprint>().is_err());
+}
+
+// ── Stores ───────────────────────────────────────────────────────────────────
+
+fn function(name: &str, line: usize, return_type: &str) -> Node {
+    Node::new(
+        NodeKind::Function,
+        name.to_string(),
+        PathBuf::from("/repo/src/a.py"),
+        line,
+        NodeData::Function {
+            parameters: vec![Parameter {
+                name: "x".to_string(),
+                param_type: Some("int".to_string()),
+                default_value: None,
+            }],
+            return_type: Some(return_type.to_string()),
+        },
+    )
+}
+
+fn graph(nodes: Vec<Node>) -> CodeGraph {
+    let mut graph = CodeGraph::new(PathBuf::from("/repo"));
+    for node in nodes {
+        graph.add_node(node);
+    }
+    graph.assign_fingerprints();
+    graph
+}
+
+fn stores() -> Vec<(&'static str, Box<dyn GraphStore>)> {
+    #[allow(unused_mut)]
+    let mut stores: Vec<(&'static str, Box<dyn GraphStore>)> =
+        vec![("memory", Box::new(MemoryStore::new()))];
+    #[cfg(feature = "cozo-store")]
+    stores.push(("cozo", Box::new(CozoStore::new_memory().unwrap())));
+    stores
+}
+
+#[test]
+fn test_changed_nodes_pair_by_fingerprint_not_position() {
+    for (name, store) in stores() {
+        store
+            .flush(
+                &graph(vec![function("a", 1, "int"), function("b", 5, "int")]),
+                "old",
+            )
+            .unwrap();
+        // Reordered: `b` is now node 0, and only its return type changed
+        store
+            .flush(
+                &graph(vec![
+                    function("b", 5, "str"),
+                    function("a", 1, "int"),
+                    function("c", 9, "int"),
+                ]),
+                "new",
+            )
+            .unwrap();
+
+        let mut changed = store.find_changed_nodes("old", "new").unwrap();
+        changed.sort_by_key(|(new, _)| new.0);
+        assert_eq!(
+            changed,
+            vec![
+                (StoreNodeId(0), Some(StoreNodeId(1))),
+                (StoreNodeId(2), None)
+            ],
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn test_reconstructed_graph_keeps_fingerprints() {
+    let original = graph(vec![function("a", 1, "int"), function("a", 5, "int")]);
+    for (name, store) in stores() {
+        store.flush(&original, "s").unwrap();
+        let graph = reconstruct_graph(store.as_ref(), "s", Path::new("/repo")).unwrap();
+        assert_eq!(graph.fingerprints(), original.fingerprints(), "{name}");
+    }
+}
+
+/// A store written before the fingerprint column existed opens with its
+/// snapshots intact, and their nodes get fingerprints on reconstruction
+#[cfg(feature = "cozo-store")]
+#[test]
+fn test_cozo_store_without_fingerprints_is_migrated() {
+    use cozo_ce::{DbInstance, ScriptMutability};
+    use std::collections::BTreeMap;
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("graph.db");
+    {
+        let db = DbInstance::new("sqlite", path.to_str().unwrap(), "").unwrap();
+        let run = |script: &str| {
+            db.run_script(script, BTreeMap::new(), ScriptMutability::Mutable)
+                .unwrap();
+        };
+        run(r#"
+            :create nodes {
+                snapshot: String, id: Int
+                =>
+                kind: String, name: String, file_path: String, line: Int,
+                end_line: Int, data_json: String, decorators_json: String,
+                type_params_json: String, hash: Int
+            }
+        "#);
+        run(r#"
+            :create edges {
+                snapshot: String, from_id: Int, to_id: Int, edge_idx: Int
+                =>
+                kind: String, metadata_json: String, hash: Int
+            }
+        "#);
+        run(":create node_removals { snapshot: String, id: Int }");
+        run(":create edge_removals { snapshot: String, from_id: Int, to_id: Int, edge_idx: Int }");
+        run(":create snapshots { name: String => node_count: Int, edge_count: Int }");
+        run(":create lineage { name: String => base: String, seq: Int, created: Int }");
+        run(r#"
+            ?[snapshot, id, kind, name, file_path, line, end_line, data_json,
+              decorators_json, type_params_json, hash] <- [[
+                'v1', 0, 'Function', 'a', '/repo/src/a.py', 1, -1,
+                '{"Function":{"parameters":[],"return_type":null}}', '[]', '[]', 0
+            ]]
+            :put nodes { snapshot, id => kind, name, file_path, line, end_line,
+                         data_json, decorators_json, type_params_json, hash }
+        "#);
+        run("?[name, node_count, edge_count] <- [['v1', 1, 0]] :put snapshots { name => node_count, edge_count }");
+        run("?[name, base, seq, created] <- [['v1', '', 0, 0]] :put lineage { name => base, seq, created }");
+    }
+
+    let store = CozoStore::new_sqlite(&path).unwrap();
+    let nodes = store.nodes("v1").unwrap();
+    assert_eq!(nodes.len(), 1);
+    assert!(nodes[0].1.fingerprint().is_none());
+
+    let graph = reconstruct_graph(&store, "v1", Path::new("/repo")).unwrap();
+    let (_, node) = graph.nodes().next().unwrap();
+    assert_eq!(node.name(), "a");
+    assert!(node.fingerprint().is_some());
+
+    // A new snapshot still pairs with the old one
+    store
+        .flush(
+            &{
+                let mut g = CodeGraph::new(PathBuf::from("/repo"));
+                let mut a = node.clone();
+                a.set_end_line(3);
+                g.add_node(a);
+                g.assign_fingerprints();
+                g
+            },
+            "v2",
+        )
+        .unwrap();
+    assert!(store.find_changed_nodes("v1", "v2").unwrap().is_empty());
+}
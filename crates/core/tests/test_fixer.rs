@@ -1,8 +1,10 @@
+mod common;
+
 use revet_core::finding::{Finding, FixKind};
 use revet_core::fixer::apply_fixes;
 use revet_core::Severity;
 use std::path::PathBuf;
-use tempfile::NamedTempFile;
+use tempfile::{NamedTempFile, TempDir};
 
 fn make_finding(file: PathBuf, line: usize, suggestion: &str, fix_kind: FixKind) -> Finding {
     Finding {
@@ -33,7 +35,8 @@ fn test_comment_out_python_secret() {
         FixKind::CommentOut,
     )];
 
-    let report = apply_fixes(&findings).unwrap();
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
     assert_eq!(report.applied, 1);
     assert_eq!(report.skipped, 0);
 
@@ -61,7 +64,8 @@ fn test_comment_out_typescript_secret() {
         FixKind::CommentOut,
     )];
 
-    let report = apply_fixes(&findings).unwrap();
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
     assert_eq!(report.applied, 1);
 
     let content = std::fs::read_to_string(&path).unwrap();
@@ -91,7 +95,8 @@ fn test_replace_pattern_infra_acl() {
         },
     )];
 
-    let report = apply_fixes(&findings).unwrap();
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
     assert_eq!(report.applied, 1);
 
     let content = std::fs::read_to_string(&path).unwrap();
@@ -119,7 +124,8 @@ fn test_replace_pattern_infra_privileged() {
         },
     )];
 
-    let report = apply_fixes(&findings).unwrap();
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
     assert_eq!(report.applied, 1);
 
     let content = std::fs::read_to_string(&path).unwrap();
@@ -147,7 +153,8 @@ fn test_replace_pickle_with_joblib() {
         },
     )];
 
-    let report = apply_fixes(&findings).unwrap();
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
     assert_eq!(report.applied, 1);
 
     let content = std::fs::read_to_string(&path).unwrap();
@@ -194,7 +201,8 @@ fn test_replace_deprecated_sklearn_import() {
         ..Default::default()
     };
 
-    let report = apply_fixes(&[f1, f2]).unwrap();
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&[f1, f2], &mut common::journal(log.path())).unwrap();
     assert_eq!(report.applied, 2);
 
     let content = std::fs::read_to_string(&path).unwrap();
@@ -220,7 +228,8 @@ fn test_suggestion_only_no_modification() {
         FixKind::Suggestion,
     )];
 
-    let report = apply_fixes(&findings).unwrap();
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
     assert_eq!(report.applied, 0);
     assert_eq!(report.skipped, 1);
 
@@ -254,7 +263,8 @@ fn test_multiple_fixes_same_file() {
     );
     f2.id = "TEST-002".to_string();
 
-    let report = apply_fixes(&[f1, f2]).unwrap();
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&[f1, f2], &mut common::journal(log.path())).unwrap();
     assert_eq!(report.applied, 2);
 
     let content = std::fs::read_to_string(&path).unwrap();
@@ -282,7 +292,8 @@ fn test_fix_preserves_other_lines() {
         },
     )];
 
-    let report = apply_fixes(&findings).unwrap();
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
     assert_eq!(report.applied, 1);
 
     let content = std::fs::read_to_string(&path).unwrap();
@@ -293,3 +304,109 @@ fn test_fix_preserves_other_lines() {
     assert!(content.contains("resource \"aws_s3_bucket\" \"data\""));
     assert!(content.contains("tags = {}"));
 }
+
+// ── RemoveSuppression tests ──────────────────────────────────────
+
+#[test]
+fn test_remove_suppression_drops_comment_only_line() {
+    let tmp = NamedTempFile::with_suffix(".ts").unwrap();
+    let path = tmp.path().to_path_buf();
+    std::fs::write(
+        &path,
+        "// revet-ignore CMD\nconst a = 1;\nconst b = 2; // revet-ignore SEC SQL\n",
+    )
+    .unwrap();
+
+    let findings = vec![
+        make_finding(
+            path.clone(),
+            1,
+            "Remove the suppression comment",
+            FixKind::RemoveSuppression { token: None },
+        ),
+        make_finding(
+            path.clone(),
+            3,
+            "Remove `SQL` from the suppression",
+            FixKind::RemoveSuppression {
+                token: Some("SQL".to_string()),
+            },
+        ),
+    ];
+
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
+    assert_eq!(report.applied, 2);
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(content, "const a = 1;\nconst b = 2; // revet-ignore SEC\n");
+}
+
+// ── RemoveStatement tests ────────────────────────────────────────
+
+#[test]
+fn test_remove_statement_deletes_line_it_fills() {
+    let tmp = NamedTempFile::with_suffix(".js").unwrap();
+    let path = tmp.path().to_path_buf();
+    std::fs::write(&path, "function f() {\n  debugger;\n  return 1;\n}\n").unwrap();
+
+    let findings = vec![make_finding(
+        path.clone(),
+        2,
+        "Remove the debugger statement",
+        FixKind::RemoveStatement {
+            statement: "debugger;".to_string(),
+        },
+    )];
+
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
+    assert_eq!(report.applied, 1);
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(content, "function f() {\n  return 1;\n}\n");
+}
+
+#[test]
+fn test_remove_statement_keeps_rest_of_line() {
+    let tmp = NamedTempFile::with_suffix(".py").unwrap();
+    let path = tmp.path().to_path_buf();
+    std::fs::write(&path, "import pdb; pdb.set_trace()\n").unwrap();
+
+    let findings = vec![make_finding(
+        path.clone(),
+        1,
+        "Remove the debugger call",
+        FixKind::RemoveStatement {
+            statement: "pdb.set_trace()".to_string(),
+        },
+    )];
+
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
+    assert_eq!(report.applied, 1);
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(content, "import pdb; \n");
+}
+
+#[test]
+fn test_remove_statement_skips_stale_line() {
+    let tmp = NamedTempFile::with_suffix(".rb").unwrap();
+    let path = tmp.path().to_path_buf();
+    std::fs::write(&path, "puts total\n").unwrap();
+
+    let findings = vec![make_finding(
+        path.clone(),
+        1,
+        "Remove the debugger call",
+        FixKind::RemoveStatement {
+            statement: "binding.pry".to_string(),
+        },
+    )];
+
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
+    assert_eq!(report.applied, 0);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "puts total\n");
+}
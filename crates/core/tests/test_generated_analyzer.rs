@@ -0,0 +1,223 @@
+mod common;
+
+use revet_core::analyzer::generated::{hash_content, GeneratedCodeAnalyzer};
+use revet_core::analyzer::Analyzer;
+use revet_core::config::RevetConfig;
+use revet_core::{apply_fixes, FixKind, Severity};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Turns each `name = value` line of the spec into an exported constant
+const GENERATOR: &str = "\
+mkdir -p src/client
+: > src/client/index.ts
+while IFS= read -r line; do
+  echo \"export const $line;\" >> src/client/index.ts
+done < api/spec.txt
+echo '// generated' > src/client/README.md
+";
+
+fn config(allow_regenerate: bool) -> RevetConfig {
+    toml::from_str(&format!(
+        r#"
+[[analyzers.generated]]
+spec = "api/spec.txt"
+output_dir = "src/client/"
+command = "sh gen.sh"
+allow_regenerate = {}
+"#,
+        allow_regenerate
+    ))
+    .unwrap()
+}
+
+fn fixture() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir_all(dir.path().join("api")).unwrap();
+    fs::write(dir.path().join("api/spec.txt"), "a = 1\nb = 2\n").unwrap();
+    fs::write(dir.path().join("gen.sh"), GENERATOR).unwrap();
+    generate(dir.path());
+    dir
+}
+
+fn generate(root: &Path) {
+    let status = std::process::Command::new("sh")
+        .arg("gen.sh")
+        .current_dir(root)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+fn run(root: &Path, config: &RevetConfig) -> Vec<revet_core::Finding> {
+    GeneratedCodeAnalyzer::from_config(config).analyze_files(&[], root)
+}
+
+#[test]
+fn test_first_run_records_hashes() {
+    let dir = fixture();
+    let findings = run(dir.path(), &config(false));
+    assert!(findings.is_empty(), "{:?}", findings);
+
+    let recorded = fs::read_to_string(dir.path().join(".revet/gen-hashes.toml")).unwrap();
+    assert!(recorded.contains("spec = \"api/spec.txt\""));
+    assert!(recorded.contains("output_dir = \"src/client\""));
+    assert!(recorded.contains("\"index.ts\""));
+}
+
+#[test]
+fn test_in_sync_has_no_findings() {
+    let dir = fixture();
+    run(dir.path(), &config(false));
+    assert!(run(dir.path(), &config(false)).is_empty());
+}
+
+#[test]
+fn test_regenerated_after_spec_change_updates_record() {
+    let dir = fixture();
+    run(dir.path(), &config(false));
+    let before = fs::read_to_string(dir.path().join(".revet/gen-hashes.toml")).unwrap();
+
+    fs::write(dir.path().join("api/spec.txt"), "a = 1\nb = 2\nc = 3\n").unwrap();
+    generate(dir.path());
+    assert!(run(dir.path(), &config(false)).is_empty());
+
+    let after = fs::read_to_string(dir.path().join(".revet/gen-hashes.toml")).unwrap();
+    assert_ne!(before, after, "record should follow the regenerated tree");
+    assert!(run(dir.path(), &config(false)).is_empty());
+}
+
+#[test]
+fn test_stale_generated_code() {
+    let dir = fixture();
+    run(dir.path(), &config(false));
+
+    fs::write(dir.path().join("api/spec.txt"), "a = 1\nb = 2\nc = 3\n").unwrap();
+    let findings = run(dir.path(), &config(false));
+    assert_eq!(findings.len(), 1, "{:?}", findings);
+    let f = &findings[0];
+    assert_eq!(f.severity, Severity::Warning);
+    assert!(f.message.contains("is stale"), "{}", f.message);
+    assert!(f.file.ends_with("api/spec.txt"));
+    assert!(f.suggestion.as_deref().unwrap().contains("sh gen.sh"));
+    assert!(matches!(f.fix_kind, Some(FixKind::Suggestion)));
+
+    // Not recorded: the warning persists until regeneration
+    assert_eq!(run(dir.path(), &config(false)).len(), 1);
+}
+
+#[test]
+fn test_hand_edited_generated_code() {
+    let dir = fixture();
+    run(dir.path(), &config(false));
+
+    fs::write(
+        dir.path().join("src/client/index.ts"),
+        "export const a = 1;\nexport const b = 42;\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("src/client/extra.ts"), "export {};\n").unwrap();
+    fs::remove_file(dir.path().join("src/client/README.md")).unwrap();
+
+    let findings = run(dir.path(), &config(false));
+    assert_eq!(findings.len(), 1, "{:?}", findings);
+    let msg = &findings[0].message;
+    assert!(msg.contains("edited by hand"), "{}", msg);
+    assert!(
+        msg.contains("README.md (removed), extra.ts (added), index.ts"),
+        "{}",
+        msg
+    );
+}
+
+#[test]
+fn test_line_endings_do_not_affect_hashes() {
+    assert_eq!(hash_content(b"a\r\nb\r\n"), hash_content(b"a\nb\n"));
+    assert_ne!(hash_content(b"a\rb"), hash_content(b"ab"));
+
+    let dir = fixture();
+    run(dir.path(), &config(false));
+    let index = dir.path().join("src/client/index.ts");
+    let crlf = fs::read_to_string(&index).unwrap().replace('\n', "\r\n");
+    fs::write(&index, crlf).unwrap();
+    assert!(run(dir.path(), &config(false)).is_empty());
+}
+
+#[test]
+fn test_missing_spec_reported() {
+    let dir = fixture();
+    fs::remove_file(dir.path().join("api/spec.txt")).unwrap();
+    let findings = run(dir.path(), &config(false));
+    assert_eq!(findings.len(), 1);
+    assert!(findings[0].message.contains("not found"));
+}
+
+#[test]
+fn test_fix_regenerates_stale_code_when_allowed() {
+    let dir = fixture();
+    run(dir.path(), &config(true));
+
+    fs::write(dir.path().join("api/spec.txt"), "a = 1\nb = 2\nc = 3\n").unwrap();
+    let findings = run(dir.path(), &config(true));
+    assert!(matches!(
+        findings[0].fix_kind,
+        Some(FixKind::Regenerate { .. })
+    ));
+
+    let log = TempDir::new().unwrap();
+    let report = apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
+    assert_eq!(report.applied, 1);
+    let index = fs::read_to_string(dir.path().join("src/client/index.ts")).unwrap();
+    assert!(index.contains("export const c = 3;"));
+    assert!(run(dir.path(), &config(true)).is_empty());
+}
+
+#[test]
+fn test_fix_restores_hand_edited_code() {
+    let dir = fixture();
+    run(dir.path(), &config(true));
+    fs::write(dir.path().join("src/client/index.ts"), "// edited\n").unwrap();
+
+    let findings = run(dir.path(), &config(true));
+    let log = TempDir::new().unwrap();
+    apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
+    assert!(run(dir.path(), &config(true)).is_empty());
+}
+
+#[test]
+fn test_fix_fails_when_generator_does_not_reproduce_output() {
+    let dir = fixture();
+    run(dir.path(), &config(true));
+    fs::write(dir.path().join("src/client/index.ts"), "// edited\n").unwrap();
+    // Generator drifted: writes a different README
+    fs::write(
+        dir.path().join("gen.sh"),
+        GENERATOR.replace("// generated", "// generated v2"),
+    )
+    .unwrap();
+
+    let findings = run(dir.path(), &config(true));
+    let log = TempDir::new().unwrap();
+    let err = apply_fixes(&findings, &mut common::journal(log.path())).unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("did not reproduce"),
+        "{:#}",
+        err
+    );
+}
+
+#[test]
+fn test_validation_requires_command_for_regenerate() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[[analyzers.generated]]
+spec = "api/openapi.yaml"
+output_dir = "src/client/"
+allow_regenerate = true
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    assert!(errors.iter().any(|e| e.contains("requires a command")));
+}
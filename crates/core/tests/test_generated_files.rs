@@ -0,0 +1,239 @@
+//! Integration tests for generated-file detection and `[files] generated`
+
+mod common;
+
+use revet_core::config::{FilesConfig, RevetConfig};
+use revet_core::generated::{GeneratedFiles, GeneratedPolicy};
+use revet_core::{
+    analyze_root, discover_files, Baseline, CoverageLedger, Finding, ParserDispatcher, Severity,
+    SkipReason,
+};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn fixture_config(policy: &str) -> RevetConfig {
+    common::fixture_config(&format!("generated_{}", policy))
+}
+
+fn default_classifier() -> GeneratedFiles {
+    GeneratedFiles::from_config(&FilesConfig::default())
+}
+
+/// (file name, severity, generated) for each finding, sorted
+fn rows(findings: &[Finding]) -> Vec<(String, Severity, bool)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .map(|f| {
+            let name = f.file.file_name().unwrap().to_string_lossy().to_string();
+            (name, f.severity, f.generated)
+        })
+        .collect();
+    rows.sort_by(|a, b| a.0.cmp(&b.0));
+    rows
+}
+
+// ── Markers ──────────────────────────────────────────────────────
+
+#[test]
+fn test_default_markers() {
+    let classifier = default_classifier();
+    for line in [
+        "// Code generated by protoc-gen-go. DO NOT EDIT.",
+        "// Code generated by mockgen. DO NOT EDIT.\r",
+        "# @generated by buck",
+        " * @generated SignedSource<<8f2d>>",
+        "// <auto-generated>",
+        "# Generated by the protocol buffer compiler.  DO NOT EDIT!",
+        "/* This file was automatically generated. */",
+        "// Auto-generated file, do not modify",
+        "# autogenerated from schema.json",
+    ] {
+        assert!(classifier.is_marker(line), "{:?} is a marker", line);
+    }
+    for line in [
+        "// Code generated by hand, edit away.",
+        "generated_at = datetime.now()",
+        "def generated(self):",
+        "// We never edit generated clients by hand",
+        "x = auto_generated_id()",
+    ] {
+        assert!(!classifier.is_marker(line), "{:?} is not a marker", line);
+    }
+}
+
+#[test]
+fn test_markers_only_checked_in_first_lines() {
+    let dir = TempDir::new().unwrap();
+    let header = dir.path().join("header.go");
+    let late = dir.path().join("late.go");
+    std::fs::write(
+        &header,
+        "// Copyright 2024\n\n// Code generated by stringer. DO NOT EDIT.\n\npackage x\n",
+    )
+    .unwrap();
+    let body: String = (0..10).map(|i| format!("// line {}\n", i)).collect();
+    std::fs::write(&late, format!("{}// @generated\n", body)).unwrap();
+
+    let classifier = default_classifier();
+    assert!(classifier.is_generated(&header));
+    assert!(!classifier.is_generated(&late));
+
+    let wide = GeneratedFiles::from_config(&FilesConfig {
+        marker_lines: 20,
+        ..FilesConfig::default()
+    });
+    assert!(wide.is_generated(&late));
+}
+
+#[test]
+fn test_custom_markers_replace_defaults() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[files]
+generated = "info-only"
+generated_markers = ["^// GENERATED FILE$"]
+"#,
+    )
+    .unwrap();
+    let classifier = GeneratedFiles::from_config(&config.files);
+    assert_eq!(classifier.policy(), GeneratedPolicy::InfoOnly);
+    assert!(classifier.is_marker("// GENERATED FILE"));
+    assert!(!classifier.is_marker("// @generated"));
+}
+
+#[test]
+fn test_validation() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[files]
+generated = "ignore"
+generated_markers = ["@generated", "([unclosed"]
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("[files] generated = \"ignore\"")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("generated_markers entry \"([unclosed\"")));
+
+    let (errors, _) = RevetConfig::default().validate();
+    assert!(!errors.iter().any(|e| e.contains("[files]")));
+    assert_eq!(RevetConfig::default().files.generated, "skip");
+}
+
+// ── Policies (fixtures) ──────────────────────────────────────────
+
+#[test]
+fn test_skip_fixture() {
+    let root = common::fixture_root("generated_skip");
+    let analysis = analyze_root(&root, &fixture_config("skip")).unwrap();
+    assert!(analysis.findings.is_empty(), "{:?}", analysis.findings);
+    // Still discovered, and parsed, for symbol resolution
+    assert_eq!(analysis.files.len(), 3);
+    let (graph, _) =
+        ParserDispatcher::new().parse_files_parallel(&analysis.files, analysis.root.clone());
+    assert!(graph
+        .nodes()
+        .any(|(_, n)| n.name() == "Greeting" && n.file_path().ends_with("api_pb2.py")));
+}
+
+#[test]
+fn test_info_only_fixture() {
+    let root = common::fixture_root("generated_info_only");
+    let analysis = analyze_root(&root, &fixture_config("info_only")).unwrap();
+    assert_eq!(
+        rows(&analysis.findings),
+        vec![
+            ("api.pb.go".to_string(), Severity::Info, true),
+            ("api_pb2.py".to_string(), Severity::Info, true),
+        ]
+    );
+}
+
+#[test]
+fn test_analyze_fixture() {
+    let root = common::fixture_root("generated_analyze");
+    let analysis = analyze_root(&root, &fixture_config("analyze")).unwrap();
+    assert_eq!(
+        rows(&analysis.findings),
+        vec![
+            ("api.pb.go".to_string(), Severity::Error, false),
+            ("api_pb2.py".to_string(), Severity::Error, false),
+        ]
+    );
+}
+
+// ── Coverage and baselines ───────────────────────────────────────
+
+#[test]
+fn test_coverage_ledger() {
+    for (policy, skipped) in [("skip", true), ("info_only", false), ("analyze", false)] {
+        let root = common::fixture_root(&format!("generated_{}", policy));
+        let config = fixture_config(policy);
+        let files = discover_files(&root, &[".py", ".go"], &[]).unwrap();
+        let generated = GeneratedFiles::from_config(&config.files).classify(&files);
+        assert_eq!(generated.len(), 2);
+
+        let mut ledger = CoverageLedger::new();
+        for file in &files {
+            ledger.discovered(file);
+            ledger.analyzed_by(file, "SEC");
+        }
+        generated.record(&mut ledger);
+
+        let pb = root.join("api_pb2.py");
+        assert!(ledger.is_generated(&pb));
+        assert!(!ledger.is_generated(&root.join("service.py")));
+        assert_eq!(
+            ledger.skip_reason(&pb),
+            skipped.then_some(SkipReason::Generated)
+        );
+
+        let report = ledger.report(&root, true);
+        assert_eq!(report.summary.generated, 2);
+        let pb_entry = report
+            .files
+            .iter()
+            .find(|f| f.path == Path::new("api_pb2.py"))
+            .unwrap();
+        assert!(pb_entry.generated);
+        let summary = report.summary.to_string();
+        if skipped {
+            assert_eq!(
+                summary,
+                "analyzed 1 of 3 discovered files; 2 skipped \u{2014} 2 generated"
+            );
+        } else {
+            assert_eq!(summary, "analyzed 3 of 3 discovered files (2 generated)");
+        }
+    }
+}
+
+#[test]
+fn test_baseline_records_generated() {
+    let root = common::fixture_root("generated_analyze");
+    let mut findings = analyze_root(&root, &fixture_config("analyze"))
+        .unwrap()
+        .findings;
+    let files = discover_files(&root, &[".py", ".go"], &[]).unwrap();
+    let generated = default_classifier().classify(&files);
+    for f in &mut findings {
+        f.generated = generated.contains(&f.file, &root);
+    }
+
+    let baseline = Baseline::from_findings(&findings, &root, None);
+    assert!(baseline.entries.iter().all(|e| e.generated));
+    let json = serde_json::to_value(&baseline).unwrap();
+    assert_eq!(json["entries"][0]["generated"], true);
+
+    // Still matches once the policy downgrades the findings
+    let mut config = fixture_config("analyze");
+    config.files.generated = "info-only".to_string();
+    let info_only = analyze_root(&root, &config).unwrap().findings;
+    let (new, suppressed) = revet_core::filter_findings(info_only, &baseline, &root);
+    assert!(new.is_empty());
+    assert_eq!(suppressed.len(), 2);
+}
@@ -0,0 +1,57 @@
+//! Tests for the clone layout revet reads objects through: sparse-checkout
+//! cones, and missing objects in a clone that is not partial
+
+use git2::{Oid, Repository};
+use revet_core::config::GitConfig;
+use revet_core::GitObjects;
+use std::fs;
+use tempfile::TempDir;
+
+fn sparse_repo(patterns: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    repo.config()
+        .unwrap()
+        .set_bool("core.sparseCheckout", true)
+        .unwrap();
+    fs::create_dir_all(repo.path().join("info")).unwrap();
+    fs::write(repo.path().join("info/sparse-checkout"), patterns).unwrap();
+    dir
+}
+
+#[test]
+fn test_cone_patterns() {
+    // `git sparse-checkout set --cone services/api docs`
+    let dir = sparse_repo("/*\n!/*/\n/services/\n!/services/*/\n/services/api/\n/docs/\n");
+    let objects = GitObjects::open(dir.path(), &GitConfig::default());
+    let sparse = objects.sparse().expect("sparse checkout detected");
+
+    assert!(sparse.contains("README.md"));
+    assert!(sparse.contains("services/Makefile"));
+    assert!(sparse.contains("services/api/main.py"));
+    assert!(sparse.contains("docs/guide/intro.md"));
+    assert!(!sparse.contains("services/web/app.py"));
+    assert!(!sparse.contains("legacy/old.py"));
+    assert!(!objects.is_partial());
+}
+
+#[test]
+fn test_not_sparse() {
+    let dir = TempDir::new().unwrap();
+    Repository::init(dir.path()).unwrap();
+    let objects = GitObjects::open(dir.path(), &GitConfig::default());
+    assert!(objects.sparse().is_none());
+    assert!(objects.in_checkout("legacy/old.py"));
+}
+
+#[test]
+fn test_missing_object_in_full_clone_is_an_error() {
+    let dir = TempDir::new().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    let objects = GitObjects::open(dir.path(), &GitConfig::default());
+    let oid = Oid::from_str("0123456789abcdef0123456789abcdef01234567").unwrap();
+
+    let err = objects.ensure(&repo, [oid]).unwrap_err();
+    assert!(err.to_string().contains("may be corrupt"), "{err}");
+    assert!(objects.degradations().is_empty());
+}
@@ -0,0 +1,287 @@
+//! Tests for Go build constraints (`//go:build` and filename suffixes) and
+//! platform-variant handling in the resolver and graph analyzers.
+
+use revet_core::analyzer::complexity::ComplexityAnalyzer;
+use revet_core::analyzer::unused_exports::UnusedExportsAnalyzer;
+use revet_core::analyzer::GraphAnalyzer;
+use revet_core::config::RevetConfig;
+use revet_core::graph::{EdgeKind, NodeKind};
+use revet_core::parser::go_build::{
+    constraint_of, file_constraint, filename_constraint, BuildExpr, Platform,
+};
+use revet_core::{CodeGraph, ParserDispatcher};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn write(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+    let path = dir.path().join(name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).unwrap();
+    }
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn platform(s: &str) -> Platform {
+    Platform::parse(s).unwrap()
+}
+
+/// `util` package with linux/windows variants of `Spawn` (5 params → complexity
+/// warning), plus `main.go` calling `util.Spawn`.
+fn variant_fixture(dir: &TempDir) -> Vec<PathBuf> {
+    let body = "func Spawn(a, b, c, d, e string) error {\n    return nil\n}\n";
+    vec![
+        write(
+            dir,
+            "util/proc_linux.go",
+            &format!("package util\n\n{}", body),
+        ),
+        write(
+            dir,
+            "util/proc_windows.go",
+            &format!("//go:build windows\n\npackage util\n\n{}", body),
+        ),
+        write(
+            dir,
+            "main.go",
+            "package main\n\nimport \"example.com/app/util\"\n\nfunc main() {\n    util.Spawn(\"a\", \"b\", \"c\", \"d\", \"e\")\n}\n",
+        ),
+    ]
+}
+
+fn parse(dir: &TempDir, files: &[PathBuf], config: &RevetConfig) -> CodeGraph {
+    let dispatcher = ParserDispatcher::new_with_config(config);
+    let (graph, errors) = dispatcher.parse_files_parallel(files, dir.path().to_path_buf());
+    assert!(errors.is_empty(), "errors: {:?}", errors);
+    graph
+}
+
+fn spawn_callers(graph: &CodeGraph) -> Vec<(String, usize)> {
+    graph
+        .nodes()
+        .filter(|(_, n)| n.name() == "Spawn")
+        .map(|(id, n)| {
+            let file = n.file_path().file_name().unwrap().to_string_lossy().into();
+            let callers = graph
+                .edges_to(id)
+                .iter()
+                .filter(|(_, e)| matches!(e.kind(), EdgeKind::Calls))
+                .count();
+            (file, callers)
+        })
+        .collect()
+}
+
+// ── Expressions ──────────────────────────────────────────────────
+
+#[test]
+fn test_parse_expression_precedence() {
+    let expr = BuildExpr::parse("linux && amd64 || !cgo").unwrap();
+    assert_eq!(expr.to_string(), "linux && amd64 || !cgo");
+
+    let expr = BuildExpr::parse("linux && (amd64 || arm64)").unwrap();
+    assert_eq!(expr.to_string(), "linux && (amd64 || arm64)");
+    assert!(expr.matches(&platform("linux/arm64")));
+    assert!(!expr.matches(&platform("linux/386")));
+    assert!(!expr.matches(&platform("darwin/arm64")));
+}
+
+#[test]
+fn test_parse_rejects_malformed() {
+    assert!(BuildExpr::parse("linux &").is_none());
+    assert!(BuildExpr::parse("(linux").is_none());
+    assert!(BuildExpr::parse("linux windows").is_none());
+}
+
+#[test]
+fn test_unix_and_implied_tags() {
+    let unix = BuildExpr::parse("unix").unwrap();
+    assert!(unix.matches(&platform("darwin/arm64")));
+    assert!(!unix.matches(&platform("windows/amd64")));
+
+    let linux = BuildExpr::parse("linux").unwrap();
+    assert!(linux.matches(&platform("android/arm64")));
+}
+
+#[test]
+fn test_unknown_tags_assumed_satisfiable() {
+    let expr = BuildExpr::parse("linux && integration").unwrap();
+    assert!(expr.matches(&platform("linux/amd64")));
+    assert!(!expr.matches(&platform("windows/amd64")));
+}
+
+#[test]
+fn test_disjoint() {
+    let linux = BuildExpr::parse("linux").unwrap();
+    let windows = BuildExpr::parse("windows").unwrap();
+    let not_windows = BuildExpr::parse("!windows").unwrap();
+    assert!(linux.is_disjoint(&windows));
+    assert!(not_windows.is_disjoint(&windows));
+    assert!(!linux.is_disjoint(&not_windows));
+}
+
+#[test]
+fn test_platform_parse() {
+    assert_eq!(platform("linux/amd64").to_string(), "linux/amd64");
+    assert!(Platform::parse("linux").is_none());
+    assert!(Platform::parse("beos/amd64").is_none());
+}
+
+// ── File constraints ─────────────────────────────────────────────
+
+#[test]
+fn test_filename_suffixes() {
+    let c = |name: &str| filename_constraint(Path::new(name)).map(|e| e.to_string());
+    assert_eq!(c("proc_linux.go").as_deref(), Some("linux"));
+    assert_eq!(c("proc_amd64.go").as_deref(), Some("amd64"));
+    assert_eq!(c("proc_linux_arm64.go").as_deref(), Some("linux && arm64"));
+    assert_eq!(c("proc_windows_test.go").as_deref(), Some("windows"));
+    assert_eq!(c("linux.go"), None);
+    assert_eq!(c("proc.go"), None);
+    assert_eq!(c("proc_helper.go"), None);
+}
+
+#[test]
+fn test_directive_combined_with_suffix() {
+    let src = "// Copyright\n\n//go:build cgo\n\npackage util\n";
+    let expr = file_constraint(Path::new("proc_linux.go"), src).unwrap();
+    assert_eq!(expr.to_string(), "linux && cgo");
+
+    let late = "package util\n\n//go:build linux\n";
+    assert!(file_constraint(Path::new("proc.go"), late).is_none());
+}
+
+#[test]
+fn test_parser_tags_all_nodes_in_file() {
+    let dir = TempDir::new().unwrap();
+    let files = variant_fixture(&dir);
+    let graph = parse(&dir, &files, &RevetConfig::default());
+
+    for (_, node) in graph.nodes() {
+        let name = node.file_path().file_name().unwrap().to_string_lossy();
+        let expected = match name.as_ref() {
+            "proc_linux.go" => Some("linux"),
+            "proc_windows.go" => Some("windows"),
+            _ => None,
+        };
+        assert_eq!(
+            constraint_of(node).map(|e| e.to_string()).as_deref(),
+            expected,
+            "{:?} in {}",
+            node.kind(),
+            name
+        );
+    }
+    assert!(graph
+        .nodes()
+        .any(|(_, n)| matches!(n.kind(), NodeKind::File) && constraint_of(n).is_some()));
+}
+
+// ── Resolution & analyzers ───────────────────────────────────────
+
+#[test]
+fn test_call_links_every_variant_by_default() {
+    let dir = TempDir::new().unwrap();
+    let files = variant_fixture(&dir);
+    let graph = parse(&dir, &files, &RevetConfig::default());
+
+    let mut callers = spawn_callers(&graph);
+    callers.sort();
+    assert_eq!(
+        callers,
+        vec![
+            ("proc_linux.go".to_string(), 1),
+            ("proc_windows.go".to_string(), 1)
+        ]
+    );
+}
+
+#[test]
+fn test_call_resolves_to_configured_platform() {
+    let dir = TempDir::new().unwrap();
+    let files = variant_fixture(&dir);
+    let mut config = RevetConfig::default();
+    config.parser.go.platforms = vec!["linux/amd64".to_string()];
+    let graph = parse(&dir, &files, &config);
+
+    let mut callers = spawn_callers(&graph);
+    callers.sort();
+    assert_eq!(
+        callers,
+        vec![
+            ("proc_linux.go".to_string(), 1),
+            ("proc_windows.go".to_string(), 0)
+        ]
+    );
+}
+
+#[test]
+fn test_variants_reported_once_with_platform_note() {
+    let dir = TempDir::new().unwrap();
+    let files = variant_fixture(&dir);
+    let config = RevetConfig::default();
+    let graph = parse(&dir, &files, &config);
+
+    let findings: Vec<_> = ComplexityAnalyzer::new()
+        .analyze_graph(&graph, &config)
+        .into_iter()
+        .filter(|f| f.message.contains("Spawn"))
+        .collect();
+    assert_eq!(findings.len(), 1, "{:?}", findings);
+    assert!(findings[0].file.ends_with("util/proc_linux.go"));
+    assert!(findings[0]
+        .message
+        .ends_with("[platform variants: linux, windows]"));
+
+    let dead: Vec<_> = UnusedExportsAnalyzer::new()
+        .analyze_graph(&graph, &config)
+        .into_iter()
+        .filter(|f| f.message.contains("Spawn"))
+        .collect();
+    assert!(dead.is_empty(), "{:?}", dead);
+}
+
+#[test]
+fn test_unused_variants_reported_once() {
+    let dir = TempDir::new().unwrap();
+    let mut files = variant_fixture(&dir);
+    files.pop(); // drop main.go — no callers
+    let config = RevetConfig::default();
+    let graph = parse(&dir, &files, &config);
+
+    let dead: Vec<_> = UnusedExportsAnalyzer::new()
+        .analyze_graph(&graph, &config)
+        .into_iter()
+        .filter(|f| f.message.contains("Spawn"))
+        .collect();
+    assert_eq!(dead.len(), 1, "{:?}", dead);
+    assert!(dead[0]
+        .message
+        .contains("[platform variants: linux, windows]"));
+}
+
+#[test]
+fn test_configured_platform_skips_other_variants() {
+    let dir = TempDir::new().unwrap();
+    let files = variant_fixture(&dir);
+    let mut config = RevetConfig::default();
+    config.parser.go.platforms = vec!["windows/amd64".to_string()];
+    let graph = parse(&dir, &files, &config);
+
+    let findings: Vec<_> = ComplexityAnalyzer::new()
+        .analyze_graph(&graph, &config)
+        .into_iter()
+        .filter(|f| f.message.contains("Spawn"))
+        .collect();
+    assert_eq!(findings.len(), 1);
+    assert!(findings[0].file.ends_with("util/proc_windows.go"));
+}
+
+#[test]
+fn test_config_rejects_bad_platform() {
+    let config: RevetConfig =
+        toml::from_str("[parser.go]\nplatforms = [\"linux/amd64\", \"linux\"]\n").unwrap();
+    let (errors, _) = config.validate();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("[parser.go]"));
+}
@@ -136,7 +136,7 @@ func (a *Animal) SetName(name string) {
     } = speak.1.data()
     {
         assert!(
-            parameters.len() >= 1,
+            !parameters.is_empty(),
             "Expected at least 1 param (receiver)"
         );
         assert_eq!(parameters[0].name, "a");
@@ -0,0 +1,35 @@
+//! Extension lookup shared by the tree-sitter analyzers
+
+use revet_core::parser::grammar::{language_for, Family};
+use std::path::Path;
+
+#[test]
+fn test_extensions_map_to_one_family() {
+    let family = |name: &str| language_for(Path::new(name)).map(|g| (g.family, g.name));
+    assert_eq!(family("app.py"), Some((Family::Python, "python")));
+    assert_eq!(family("lib.mjs"), Some((Family::Js, "javascript")));
+    assert_eq!(family("api.MTS"), Some((Family::Js, "typescript")));
+    assert_eq!(family("View.tsx"), Some((Family::Js, "tsx")));
+    assert_eq!(family("Main.kts"), Some((Family::Kotlin, "kotlin")));
+    assert_eq!(family("Pay.cs"), Some((Family::CSharp, "csharp")));
+    assert_eq!(family("README.md"), None);
+    assert_eq!(family("Makefile"), None);
+}
+
+#[test]
+fn test_families_without_a_grammar() {
+    assert_eq!(Family::of(Path::new("build.groovy")), Some(Family::Groovy));
+    assert!(language_for(Path::new("build.groovy")).is_none());
+    for name in [
+        "a.py", "a.ts", "a.java", "a.kt", "a.go", "a.rs", "a.rb", "a.php", "a.swift",
+    ] {
+        let grammar = language_for(Path::new(name)).unwrap();
+        assert_eq!(
+            Family::of(Path::new(name)),
+            Some(grammar.family),
+            "{}",
+            name
+        );
+        assert!(grammar.parse("").is_some(), "{}", name);
+    }
+}
@@ -1,7 +1,7 @@
 //! Tests for graph data structures (nodes, edges, queries)
 
 use revet_core::graph::{
-    CodeGraph, Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeKind, Parameter,
+    CodeGraph, Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeKind, Parameter, Violation,
 };
 use std::path::PathBuf;
 
@@ -236,3 +236,130 @@ fn test_merge_edges_integrity() {
     assert_eq!(deps.len(), 1);
     assert_eq!(deps[0], map[&s1]);
 }
+
+// ── Validation tests ────────────────────────────────────────────
+
+fn file_node(path: &str) -> Node {
+    Node::new(
+        NodeKind::File,
+        path.to_string(),
+        PathBuf::from(path),
+        0,
+        NodeData::File {
+            language: "python".to_string(),
+            code_kind: Default::default(),
+        },
+    )
+}
+
+fn function_node(name: &str, path: &str) -> Node {
+    Node::new(
+        NodeKind::Function,
+        name.to_string(),
+        PathBuf::from(path),
+        1,
+        NodeData::Function {
+            parameters: vec![],
+            return_type: None,
+        },
+    )
+}
+
+#[test]
+fn test_validate_contained_graph() {
+    let mut graph = CodeGraph::new(PathBuf::from("/root"));
+    let file = graph.add_node(file_node("a.py"));
+    let class = graph.add_node(Node::new(
+        NodeKind::Class,
+        "A".to_string(),
+        PathBuf::from("a.py"),
+        1,
+        NodeData::Class {
+            base_classes: vec![],
+            methods: vec!["run".to_string()],
+            fields: vec![],
+        },
+    ));
+    let method = graph.add_node(function_node("A.run", "a.py"));
+    let import = graph.add_node(Node::new(
+        NodeKind::Import,
+        "os".to_string(),
+        PathBuf::from("a.py"),
+        1,
+        NodeData::Import {
+            module: "os".to_string(),
+            imported_names: vec!["os".to_string()],
+            resolved_path: None,
+        },
+    ));
+    graph.add_edge(file, class, Edge::new(EdgeKind::Contains));
+    graph.add_edge(class, method, Edge::new(EdgeKind::Contains));
+    graph.add_edge(file, import, Edge::new(EdgeKind::Imports));
+
+    assert_eq!(graph.validate(), Ok(()));
+}
+
+#[test]
+fn test_validate_reports_uncontained_node() {
+    let mut graph = CodeGraph::new(PathBuf::from("/root"));
+    let file = graph.add_node(file_node("a.py"));
+    let top = graph.add_node(function_node("top", "a.py"));
+    let stray = graph.add_node(function_node("stray", "a.py"));
+    graph.add_edge(file, top, Edge::new(EdgeKind::Contains));
+    // A call edge does not make the callee part of the file
+    graph.add_edge(top, stray, Edge::new(EdgeKind::Calls));
+
+    let violations = graph.validate().unwrap_err();
+    assert_eq!(
+        violations,
+        vec![Violation::Uncontained {
+            node: stray,
+            name: "stray".to_string(),
+            file: PathBuf::from("a.py"),
+        }]
+    );
+}
+
+#[test]
+fn test_validate_does_not_contain_across_files() {
+    let mut graph = CodeGraph::new(PathBuf::from("/root"));
+    let a = graph.add_node(file_node("a.py"));
+    graph.add_node(file_node("b.py"));
+    let func = graph.add_node(function_node("f", "b.py"));
+    graph.add_edge(a, func, Edge::new(EdgeKind::Contains));
+
+    let violations = graph.validate().unwrap_err();
+    assert!(matches!(
+        violations.as_slice(),
+        [Violation::Uncontained { node, .. }] if *node == func
+    ));
+}
+
+#[test]
+fn test_validate_reports_node_without_file() {
+    let mut graph = CodeGraph::new(PathBuf::from("/root"));
+    let func = graph.add_node(function_node("f", "orphan.py"));
+
+    let violations = graph.validate().unwrap_err();
+    assert_eq!(
+        violations,
+        vec![Violation::MissingFile {
+            node: func,
+            file: PathBuf::from("orphan.py"),
+        }]
+    );
+}
+
+#[test]
+fn test_validate_after_remove_node() {
+    let mut graph = CodeGraph::new(PathBuf::from("/root"));
+    let file = graph.add_node(file_node("a.py"));
+    let first = graph.add_node(function_node("first", "a.py"));
+    let last = graph.add_node(function_node("last", "a.py"));
+    graph.add_edge(file, first, Edge::new(EdgeKind::Contains));
+    graph.add_edge(file, last, Edge::new(EdgeKind::Contains));
+
+    // `last` moves into the removed node's slot; the index must follow
+    graph.remove_node(first);
+    assert_eq!(graph.validate(), Ok(()));
+}
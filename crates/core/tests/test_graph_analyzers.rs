@@ -2,8 +2,9 @@
 
 use revet_core::config::RevetConfig;
 use revet_core::finding::Severity;
-use revet_core::graph::{CodeGraph, Edge, EdgeKind, Node, NodeData, NodeId, NodeKind};
+use revet_core::graph::{CodeGraph, Edge, EdgeKind, ImportKind, Node, NodeData, NodeId, NodeKind};
 use revet_core::AnalyzerDispatcher;
+use revet_core::CodeKind;
 use std::path::PathBuf;
 
 // ── Helpers ──────────────────────────────────────────────────────────────────
@@ -23,6 +24,7 @@ fn add_file_node(graph: &mut CodeGraph, path: &str) -> revet_core::graph::NodeId
         0,
         NodeData::File {
             language: "python".to_string(),
+            code_kind: CodeKind::Production,
         },
     ))
 }
@@ -231,6 +233,32 @@ fn test_cycles_disabled_no_findings() {
     );
 }
 
+#[test]
+fn test_type_only_cycle_ignored() {
+    let mut graph = CodeGraph::new(PathBuf::from("."));
+    let file_a = add_file_node(&mut graph, "src/a.ts");
+    let file_b = add_file_node(&mut graph, "src/b.ts");
+    let file_c = add_file_node(&mut graph, "src/c.ts");
+
+    // A⇢B⇢A with `import type` both ways: erased at runtime
+    graph.add_edge(file_a, file_b, Edge::import(ImportKind::TypeOnly));
+    graph.add_edge(file_b, file_a, Edge::import(ImportKind::TypeOnly));
+    // B⇢C type-only, C→B at runtime: still a cycle
+    graph.add_edge(file_b, file_c, Edge::import(ImportKind::TypeOnly));
+    graph.add_edge(file_c, file_b, Edge::import(ImportKind::Runtime));
+
+    let dispatcher = AnalyzerDispatcher::new();
+    let config = config_with(false, true);
+    let findings = dispatcher.run_graph_analyzers(&graph, &config);
+
+    let cycles: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("CYCLE"))
+        .collect();
+    assert_eq!(cycles.len(), 1, "{:?}", findings);
+    assert!(cycles[0].message.contains("b.ts → c.ts → b.ts"));
+}
+
 // ── Diff-scoped dead code helpers (rvt-61) ────────────────────────────────────
 
 fn add_public_function(graph: &mut CodeGraph, name: &str, file: &str, line: usize) -> NodeId {
@@ -0,0 +1,219 @@
+//! Task-runner hash inputs: every input class moves the digest, and
+//! nothing machine-specific does
+
+use proptest::prelude::*;
+use revet_core::hash_inputs::{content_hash, HashInputs, Project};
+use revet_core::{discover_files, ProfileSettings, RevetConfig};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const CONFIG: &str = "\
+[[project]]
+path = \"packages/api\"
+depends_on = [\"packages/shared\"]
+";
+
+/// `packages/api` depending on `packages/shared`, and an unrelated
+/// `packages/web`
+fn monorepo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    fs::write(root.join(".revet.toml"), CONFIG).unwrap();
+    for (project, file, body) in [
+        (
+            "api",
+            "index.js",
+            "const util = require('../shared/util');\n",
+        ),
+        ("shared", "util.js", "module.exports = {};\n"),
+        ("web", "app.js", "console.log('web');\n"),
+    ] {
+        let path = root.join("packages").join(project);
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("package.json"), "{}\n").unwrap();
+        fs::write(path.join(file), body).unwrap();
+    }
+    dir
+}
+
+fn inputs(root: &Path) -> HashInputs {
+    let root = root.canonicalize().unwrap();
+    let config = RevetConfig::find_and_load(&root).unwrap();
+    let settings = ProfileSettings::resolve(None, &config).unwrap();
+    let project = Project::resolve(&root, Path::new("packages/api"), &config).unwrap();
+    let files = discover_files(&root, &[".js", ".json"], &config.ignore.paths).unwrap();
+    let baseline = root.join("baseline.json");
+    HashInputs::collect(
+        &root,
+        &project,
+        &files,
+        &config,
+        &settings,
+        &["security", "dead_code"],
+        Some(&baseline),
+    )
+    .unwrap()
+}
+
+fn digest(root: &Path) -> String {
+    inputs(root).digest()
+}
+
+fn append(path: &Path, text: &str) {
+    let mut content = fs::read_to_string(path).unwrap();
+    content.push_str(text);
+    fs::write(path, content).unwrap();
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn prop_line_endings_do_not_change_the_hash(lines in prop::collection::vec("[ -~]{0,20}", 0..8)) {
+        prop_assert_eq!(
+            content_hash(lines.join("\r\n").as_bytes()),
+            content_hash(lines.join("\n").as_bytes())
+        );
+    }
+
+    #[test]
+    fn prop_any_content_change_moves_the_hash(
+        content in prop::collection::vec(any::<u8>(), 0..64),
+        at in any::<prop::sample::Index>(),
+        byte in any::<u8>(),
+    ) {
+        let mut changed = content.clone();
+        changed.insert(at.index(content.len() + 1), byte);
+        prop_assert_ne!(content_hash(&content), content_hash(&changed));
+    }
+}
+
+#[test]
+fn test_each_input_class_moves_the_digest() {
+    type Mutation = fn(&Path);
+    let mutations: [(&str, Mutation); 9] = [
+        ("file content", |root| {
+            append(&root.join("packages/api/index.js"), "eval(x);\n")
+        }),
+        ("added file", |root| {
+            fs::write(root.join("packages/api/extra.js"), "\n").unwrap()
+        }),
+        ("deleted file", |root| {
+            fs::remove_file(root.join("packages/api/index.js")).unwrap()
+        }),
+        ("renamed file", |root| {
+            fs::rename(
+                root.join("packages/api/index.js"),
+                root.join("packages/api/main.js"),
+            )
+            .unwrap()
+        }),
+        ("dependency file", |root| {
+            append(&root.join("packages/shared/util.js"), "// changed\n")
+        }),
+        ("config", |root| {
+            append(&root.join(".revet.toml"), "[modules]\ncycles = false\n")
+        }),
+        ("profile", |root| {
+            append(&root.join(".revet.toml"), "[general]\nprofile = \"deep\"\n")
+        }),
+        ("declared dependencies", |root| {
+            fs::write(
+                root.join(".revet.toml"),
+                "[[project]]\npath = \"packages/api\"\ndepends_on = [\"packages/web\"]\n",
+            )
+            .unwrap()
+        }),
+        ("baseline", |root| {
+            fs::write(root.join("baseline.json"), "{\"entries\": []}\n").unwrap()
+        }),
+    ];
+    for (input, mutate) in mutations {
+        let repo = monorepo();
+        let before = digest(repo.path());
+        mutate(repo.path());
+        assert_ne!(
+            digest(repo.path()),
+            before,
+            "{} did not move the digest",
+            input
+        );
+    }
+
+    // Analyzers and their versions
+    let repo = monorepo();
+    let original = inputs(repo.path());
+    let mut changed = original.clone();
+    changed
+        .analyzers
+        .insert("security".to_string(), "0.0.0".to_string());
+    assert_ne!(changed.digest(), original.digest());
+    let mut changed = original.clone();
+    changed.analyzers.remove("dead_code");
+    assert_ne!(changed.digest(), original.digest());
+}
+
+#[test]
+fn test_unrelated_changes_keep_the_digest() {
+    let repo = monorepo();
+    let before = digest(repo.path());
+    append(&repo.path().join("packages/web/app.js"), "eval(x);\n");
+    append(
+        &repo.path().join(".revet.toml"),
+        "[storage]\ndata_dir = \"/tmp/elsewhere\"\n",
+    );
+    assert_eq!(digest(repo.path()), before);
+}
+
+#[test]
+fn test_digest_is_stable_across_checkouts() {
+    let (first, second) = (monorepo(), monorepo());
+    assert_eq!(digest(first.path()), digest(second.path()));
+
+    // A Windows checkout with CRLF line endings hashes the same
+    let index = second.path().join("packages/api/index.js");
+    let content = fs::read_to_string(&index).unwrap().replace('\n', "\r\n");
+    fs::write(&index, content).unwrap();
+    assert_eq!(digest(first.path()), digest(second.path()));
+
+    let inputs = inputs(first.path());
+    assert_eq!(inputs.depends_on, ["packages/shared"]);
+    let files: Vec<&str> = inputs.files.keys().map(String::as_str).collect();
+    assert_eq!(
+        files,
+        [
+            "packages/api/index.js",
+            "packages/api/package.json",
+            "packages/shared/package.json",
+            "packages/shared/util.js"
+        ]
+    );
+    assert!(!serde_json::to_string(&inputs)
+        .unwrap()
+        .contains(&*first.path().to_string_lossy()));
+}
+
+#[test]
+fn test_project_must_be_a_project_directory() {
+    let repo = monorepo();
+    let root = repo.path().canonicalize().unwrap();
+    let config = RevetConfig::find_and_load(&root).unwrap();
+    fs::create_dir_all(root.join("docs")).unwrap();
+
+    let error = |dir: &str| {
+        Project::resolve(&root, Path::new(dir), &config)
+            .unwrap_err()
+            .to_string()
+    };
+    assert!(error("docs").contains("not a project"));
+    assert!(error("packages/missing").contains("no such directory"));
+    assert!(error("../elsewhere").contains("inside the repository"));
+
+    // A manifest makes a project without a [[project]] entry
+    let web = Project::resolve(&root, &root.join("packages/web"), &config).unwrap();
+    assert_eq!(web.path, "packages/web");
+    assert!(web.depends_on.is_empty());
+    assert!(web.contains(&root, &root.join("packages/web/app.js")));
+    assert!(!web.contains(&root, &root.join("packages/webapp/app.js")));
+}
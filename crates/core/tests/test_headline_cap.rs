@@ -0,0 +1,63 @@
+//! Every built-in analyzer keeps `Finding.message` a short headline: over
+//! each fixture, with every module switched on, no message may exceed
+//! [`HEADLINE_MAX`]. Long-form content belongs in `Finding.details`.
+
+mod common;
+
+use revet_core::config::{ModulesConfig, RevetConfig};
+use revet_core::finding::HEADLINE_MAX;
+use revet_core::ParserDispatcher;
+
+/// `modules` with every on/off switch turned on
+fn all_modules(modules: &ModulesConfig) -> ModulesConfig {
+    let mut value = toml::Value::try_from(modules).unwrap();
+    for (_, v) in value.as_table_mut().unwrap().iter_mut() {
+        if v.is_bool() {
+            *v = toml::Value::Boolean(true);
+        }
+    }
+    value.try_into().unwrap()
+}
+
+#[test]
+fn test_headlines_stay_under_the_cap() {
+    let parsers = ParserDispatcher::new();
+    let extensions = parsers.supported_extensions();
+    let fixtures = common::fixture_root(".");
+
+    let mut long = Vec::new();
+    let mut checked = 0;
+    for entry in std::fs::read_dir(fixtures).unwrap() {
+        let root = entry.unwrap().path();
+        if !root.is_dir() {
+            continue;
+        }
+        let mut config = match root.join(".revet.toml") {
+            toml if toml.exists() => RevetConfig::from_file(&toml).unwrap(),
+            _ => RevetConfig::default(),
+        };
+        config.modules = all_modules(&config.modules);
+        for finding in common::run(&root, &extensions, &config) {
+            checked += 1;
+            let chars = finding.message.chars().count();
+            if chars > HEADLINE_MAX {
+                long.push(format!(
+                    "{} {}:{} ({} chars): {}",
+                    finding.id,
+                    finding.file.display(),
+                    finding.line,
+                    chars,
+                    finding.message
+                ));
+            }
+        }
+    }
+    assert!(checked > 0, "the fixtures produced no findings");
+    assert!(
+        long.is_empty(),
+        "{} headline(s) over {} chars; move the long form into `details`:\n{}",
+        long.len(),
+        HEADLINE_MAX,
+        long.join("\n")
+    );
+}
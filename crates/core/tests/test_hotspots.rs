@@ -0,0 +1,318 @@
+//! Hotspot analysis: churn from a scripted git history, combined with
+//! aggregate complexity.
+//!
+//! The fixture repository has fixed commit times, so the expected change
+//! count per file inside the window is known exactly:
+//!
+//! | file         | commits in window (days 110–200) | complexity |
+//! |--------------|----------------------------------|------------|
+//! | `billing.py` | 5                                | 12         |
+//! | `report.py`  | 4 (2 of them as `old_name.py`)   | 6          |
+//! | `util.py`    | 2                                | 2          |
+//! | `legacy.py`  | 0 (changed on day 50 only)       | 15         |
+
+mod common;
+
+use common::{commit, python_fn};
+use git2::Repository;
+use revet_core::analyzer::hotspots::{rank, Hotspot};
+use revet_core::churn::{Churn, Window};
+use revet_core::config::RevetConfig;
+use revet_core::graph::{CodeGraph, Node, NodeData, NodeKind};
+use revet_core::storage::StoragePaths;
+use revet_core::AnalyzerDispatcher;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+const DEV: (&str, &str) = ("dev", "dev@example.com");
+
+// ── Fixture ───────────────────────────────────────────────────────────────────
+
+/// Body of `file` after its `n`-th edit: the function plus `n` comment lines
+fn edited(function: &str, n: usize) -> Option<String> {
+    let mut src = function.to_string();
+    for i in 0..n {
+        src.push_str(&format!("# edit {}\n", i + 1));
+    }
+    Some(src)
+}
+
+fn fixture() -> (TempDir, Repository) {
+    let dir = TempDir::new().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    let billing = python_fn("charge", 11);
+    let report = python_fn("render", 5);
+    let util = python_fn("clamp", 1);
+    let legacy = python_fn("convert", 14);
+
+    commit(
+        &repo,
+        DEV,
+        &[
+            ("billing.py", edited(&billing, 0)),
+            ("util.py", edited(&util, 0)),
+            ("legacy.py", edited(&legacy, 0)),
+            ("old_name.py", edited(&report, 0)),
+        ],
+        10,
+    );
+    commit(&repo, DEV, &[("legacy.py", edited(&legacy, 1))], 50);
+    commit(&repo, DEV, &[("old_name.py", edited(&report, 1))], 120);
+    commit(&repo, DEV, &[("billing.py", edited(&billing, 1))], 120);
+    commit(&repo, DEV, &[("old_name.py", edited(&report, 2))], 125);
+    commit(
+        &repo,
+        DEV,
+        &[
+            ("billing.py", edited(&billing, 2)),
+            ("util.py", edited(&util, 1)),
+        ],
+        130,
+    );
+    commit(
+        &repo,
+        DEV,
+        &[("old_name.py", None), ("report.py", edited(&report, 2))],
+        140,
+    );
+    commit(&repo, DEV, &[("report.py", edited(&report, 3))], 150);
+    commit(&repo, DEV, &[("billing.py", edited(&billing, 3))], 160);
+    commit(&repo, DEV, &[("billing.py", edited(&billing, 4))], 170);
+    commit(
+        &repo,
+        DEV,
+        &[
+            ("billing.py", edited(&billing, 5)),
+            ("util.py", edited(&util, 2)),
+        ],
+        200,
+    );
+    (dir, repo)
+}
+
+/// Function nodes for the fixture's files at HEAD
+fn graph(root: &Path) -> CodeGraph {
+    let mut graph = CodeGraph::new(root.to_path_buf());
+    for (file, name, branches) in [
+        ("billing.py", "charge", 11),
+        ("report.py", "render", 5),
+        ("util.py", "clamp", 1),
+        ("legacy.py", "convert", 14),
+    ] {
+        let mut node = Node::new(
+            NodeKind::Function,
+            name.to_string(),
+            root.join(file),
+            1,
+            NodeData::Function {
+                parameters: Vec::new(),
+                return_type: None,
+            },
+        );
+        node.set_end_line(branches + 2);
+        graph.add_node(node);
+    }
+    graph
+}
+
+fn config(percentile: u8) -> RevetConfig {
+    let mut config = RevetConfig::default();
+    config.modules.hotspots = true;
+    config.analyzers.hotspots.percentile = percentile;
+    config
+}
+
+fn hotspot(file: &str, changes: usize, complexity: usize, flagged: bool) -> Hotspot {
+    Hotspot {
+        file: file.to_string(),
+        changes,
+        complexity,
+        flagged,
+    }
+}
+
+// ── Window ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_window_parse() {
+    assert_eq!(Window::parse("90d"), Some(Window { days: 90 }));
+    assert_eq!(Window::parse("2w"), Some(Window { days: 14 }));
+    assert_eq!(Window::parse("6m"), Some(Window { days: 180 }));
+    assert_eq!(Window::parse("1y"), Some(Window { days: 365 }));
+    for invalid in ["", "90", "0d", "d", "-3d", "90 days", "1.5w"] {
+        assert_eq!(Window::parse(invalid), None, "{invalid:?}");
+    }
+}
+
+#[test]
+fn test_config_validates_hotspot_settings() {
+    let config: RevetConfig = toml::from_str(
+        "[modules]\nhotspots = true\n\n[analyzers.hotspots]\nwindow = \"6m\"\npercentile = 80\n",
+    )
+    .unwrap();
+    assert!(config.modules.hotspots);
+    assert_eq!(config.analyzers.hotspots.window, "6m");
+    assert_eq!(config.analyzers.hotspots.min_changes, 2);
+    assert!(config.validate().0.is_empty());
+
+    let config: RevetConfig =
+        toml::from_str("[analyzers.hotspots]\nwindow = \"90\"\npercentile = 100\n").unwrap();
+    let (errors, _) = config.validate();
+    assert!(errors.iter().any(|e| e.contains("window = \"90\"")));
+    assert!(errors.iter().any(|e| e.contains("percentile = 100")));
+}
+
+// ── Churn ─────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_churn_counts_commits_in_window_and_follows_renames() {
+    let (_dir, repo) = fixture();
+    let churn = Churn::compute(&repo, Window { days: 90 }).unwrap();
+
+    assert_eq!(churn.commits, 9);
+    assert!(!churn.shallow);
+    assert_eq!(churn.changes(Path::new("billing.py")), 5);
+    assert_eq!(churn.changes(Path::new("report.py")), 4);
+    assert_eq!(churn.changes(Path::new("util.py")), 2);
+    assert_eq!(churn.changes(Path::new("legacy.py")), 0);
+    assert_eq!(churn.changes(Path::new("old_name.py")), 0);
+}
+
+#[test]
+fn test_churn_window_ends_at_head_commit() {
+    let (_dir, repo) = fixture();
+    let churn = Churn::compute(&repo, Window { days: 365 }).unwrap();
+    assert_eq!(churn.commits, 11);
+    assert_eq!(churn.changes(Path::new("billing.py")), 6);
+    assert_eq!(churn.changes(Path::new("report.py")), 5);
+    assert_eq!(churn.changes(Path::new("legacy.py")), 2);
+
+    let churn = Churn::compute(&repo, Window { days: 30 }).unwrap();
+    assert_eq!(churn.commits, 2);
+    assert_eq!(churn.changes(Path::new("billing.py")), 2);
+    assert_eq!(churn.changes(Path::new("report.py")), 0);
+}
+
+#[test]
+fn test_churn_is_cached_per_head_and_window() {
+    let (dir, repo) = fixture();
+    let window = Window { days: 90 };
+    let paths = StoragePaths::in_repo(dir.path());
+    let first = Churn::load_or_compute(dir.path(), &paths, window).unwrap();
+    let cache = dir.path().join(".revet-cache/churn.json");
+    assert!(cache.exists());
+
+    // A cache hit returns the stored counts without walking the history
+    let mut tampered = first.clone();
+    tampered.changes.insert("billing.py".to_string(), 99);
+    fs::write(&cache, serde_json::to_vec(&tampered).unwrap()).unwrap();
+    let cached = Churn::load_or_compute(dir.path(), &paths, window).unwrap();
+    assert_eq!(cached.changes(Path::new("billing.py")), 99);
+
+    // Another window or a new HEAD walks again
+    let other = Churn::load_or_compute(dir.path(), &paths, Window { days: 91 }).unwrap();
+    assert_eq!(other.changes(Path::new("billing.py")), 5);
+    commit(
+        &repo,
+        DEV,
+        &[("util.py", edited(&python_fn("clamp", 1), 3))],
+        201,
+    );
+    let fresh = Churn::load_or_compute(dir.path(), &paths, window).unwrap();
+    assert_ne!(fresh.head, first.head);
+    assert_eq!(fresh.changes(Path::new("billing.py")), 5);
+    assert_eq!(fresh.changes(Path::new("util.py")), 3);
+}
+
+#[test]
+fn test_churn_in_shallow_clone_uses_available_history() {
+    let (dir, _repo) = fixture();
+    let clone_dir = TempDir::new().unwrap();
+    let status = Command::new("git")
+        .args(["clone", "-q", "--depth", "3"])
+        .arg(format!("file://{}", dir.path().display()))
+        .arg(clone_dir.path().join("clone"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let clone = Repository::open(clone_dir.path().join("clone")).unwrap();
+    let churn = Churn::compute(&clone, Window { days: 90 }).unwrap();
+    assert!(churn.shallow);
+    // Day 200 and day 170; the day-160 boundary commit has no parent to diff
+    assert_eq!(churn.commits, 2);
+    assert_eq!(churn.changes(Path::new("billing.py")), 2);
+    assert_eq!(churn.changes(Path::new("util.py")), 1);
+}
+
+// ── Ranking ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_rank_orders_by_score_and_flags_top_percentile_of_both() {
+    let files = vec![
+        ("util.py".to_string(), 2, 2),
+        ("legacy.py".to_string(), 0, 15),
+        ("billing.py".to_string(), 5, 12),
+        ("report.py".to_string(), 4, 6),
+    ];
+    assert_eq!(
+        rank(files.clone(), 50, 2),
+        vec![
+            hotspot("billing.py", 5, 12, true),
+            hotspot("report.py", 4, 6, false),
+            hotspot("util.py", 2, 2, false),
+        ]
+    );
+    // legacy.py has the highest complexity, so billing.py is no longer in
+    // the top 10% of it
+    assert!(rank(files.clone(), 90, 2).iter().all(|h| !h.flagged));
+    // min_changes keeps rarely changed files out
+    assert!(rank(files, 50, 6).iter().all(|h| !h.flagged));
+}
+
+#[test]
+fn test_hotspot_report_and_findings() {
+    let (dir, _repo) = fixture();
+    let root = dir.path();
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config(50));
+
+    let (findings, report) = dispatcher
+        .run_hotspots(&graph(root), root, &config(50))
+        .expect("module on")
+        .unwrap();
+    assert_eq!(
+        report.ranking,
+        vec![
+            hotspot("billing.py", 5, 12, true),
+            hotspot("report.py", 4, 6, false),
+            hotspot("util.py", 2, 2, false),
+        ]
+    );
+    assert_eq!(report.ranking[0].score(), 60);
+
+    assert_eq!(findings.len(), 1);
+    let finding = &findings[0];
+    assert_eq!(finding.id, "HOT-001");
+    assert_eq!(finding.file, root.join("billing.py"));
+    assert_eq!(finding.line, 1);
+    assert_eq!(
+        finding.message,
+        "Hotspot: changed 5 times in 90 days, aggregate complexity 12"
+    );
+}
+
+#[test]
+fn test_hotspots_off_by_default_and_without_git() {
+    let dir = TempDir::new().unwrap();
+    let dispatcher = AnalyzerDispatcher::new();
+    let graph = graph(dir.path());
+    assert!(dispatcher
+        .run_hotspots(&graph, dir.path(), &RevetConfig::default())
+        .is_none());
+    assert!(dispatcher
+        .run_hotspots(&graph, dir.path(), &config(90))
+        .unwrap()
+        .is_err());
+}
@@ -0,0 +1,353 @@
+//! Importing external tools' reports: SARIF, gitleaks and Semgrep JSON
+//! parsing, path resolution and merging into a run's findings
+
+use revet_core::import::{self, gitleaks, sarif, semgrep, ImportFormat, ImportOptions};
+use revet_core::{Finding, Severity};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const ROOT: &str = "/repo";
+
+fn options() -> ImportOptions {
+    ImportOptions::new(Path::new(ROOT))
+}
+
+const SEMGREP_SARIF: &str = r#"{
+  "version": "2.1.0",
+  "runs": [{
+    "tool": {"driver": {"name": "Semgrep OSS", "rules": [
+      {"id": "python.lang.security.audit.eval-detected",
+       "shortDescription": {"text": "Detected the use of eval()"},
+       "defaultConfiguration": {"level": "warning"}},
+      {"id": "python.flask.debug-enabled",
+       "shortDescription": {"text": "Flask debug mode"},
+       "defaultConfiguration": {"level": "error"},
+       "messageStrings": {"default": {"text": "Debug mode is on"}}}
+    ]}},
+    "originalUriBaseIds": {"SRC": {"uri": "file:///ci/work/"}},
+    "results": [
+      {"ruleId": "python.lang.security.audit.eval-detected", "level": "error",
+       "message": {"text": "eval() on user input"},
+       "locations": [{"physicalLocation": {
+         "artifactLocation": {"uri": "app/views.py"},
+         "region": {"startLine": 12, "startColumn": 5, "endLine": 12, "endColumn": 22}}}]},
+      {"ruleIndex": 1, "message": {"id": "default"},
+       "locations": [{"physicalLocation": {
+         "artifactLocation": {"uri": "app/main.py", "uriBaseId": "SRC"},
+         "region": {"startLine": 3, "startColumn": 1, "endLine": 5, "endColumn": 2}}}]},
+      {"ruleId": "python.lang.security.audit.eval-detected", "kind": "pass",
+       "message": {"text": "ok"},
+       "locations": [{"physicalLocation": {"artifactLocation": {"uri": "app/ok.py"}, "region": {"startLine": 1}}}]},
+      {"ruleId": "python.lang.security.audit.eval-detected",
+       "message": {"text": "accepted"},
+       "suppressions": [{"kind": "external", "status": "accepted"}],
+       "locations": [{"physicalLocation": {"artifactLocation": {"uri": "app/old.py"}, "region": {"startLine": 9}}}]}
+    ]
+  }]
+}"#;
+
+// ── SARIF ─────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_sarif_results_keep_rule_severity_and_location() {
+    let mut options = options();
+    options.strip_prefix = Some("/ci/work".to_string());
+    let findings = sarif::parse(SEMGREP_SARIF, &options).unwrap();
+    assert_eq!(findings.len(), 2, "pass and suppressed results are skipped");
+
+    let eval = &findings[0];
+    assert_eq!(
+        eval.rule.as_deref(),
+        Some("EXT-semgrep-python.lang.security.audit.eval-detected")
+    );
+    assert_eq!(eval.source_tool.as_deref(), Some("semgrep"));
+    assert_eq!(eval.severity, Severity::Error, "result level wins");
+    assert_eq!(eval.message, "eval() on user input");
+    assert_eq!(eval.file, PathBuf::from("/repo/app/views.py"));
+    assert_eq!(
+        (eval.line, eval.column, eval.end_column),
+        (12, Some(5), Some(22))
+    );
+    assert_eq!(
+        eval.rule_description.as_deref(),
+        Some("Detected the use of eval()")
+    );
+
+    // Rule by index, level and message from the rule, base URI expanded
+    let debug = &findings[1];
+    assert_eq!(
+        debug.rule.as_deref(),
+        Some("EXT-semgrep-python.flask.debug-enabled")
+    );
+    assert_eq!(debug.severity, Severity::Error);
+    assert_eq!(debug.message, "Debug mode is on");
+    assert_eq!(debug.file, PathBuf::from("/repo/app/main.py"));
+    assert_eq!((debug.column, debug.end_column), (Some(1), None));
+}
+
+#[test]
+fn test_sarif_levels_and_invalid_documents() {
+    assert_eq!(sarif::severity("error"), Severity::Error);
+    assert_eq!(sarif::severity("warning"), Severity::Warning);
+    assert_eq!(sarif::severity("note"), Severity::Info);
+    assert_eq!(sarif::severity("none"), Severity::Info);
+
+    // Without a level anywhere, SARIF's default is warning
+    let doc = r#"{"runs": [{"tool": {"driver": {"name": "CodeQL"}}, "results": [
+        {"ruleId": "js/xss", "message": {"text": "XSS"},
+         "locations": [{"physicalLocation": {"artifactLocation": {"uri": "a.js"}, "region": {"startLine": 2}}}]}
+    ]}]}"#;
+    let findings = sarif::parse(doc, &options()).unwrap();
+    assert_eq!(findings[0].severity, Severity::Warning);
+    assert_eq!(findings[0].rule.as_deref(), Some("EXT-codeql-js/xss"));
+
+    assert!(sarif::parse("{}", &options()).is_err());
+    assert!(sarif::parse("not json", &options()).is_err());
+}
+
+#[test]
+fn test_sarif_result_without_location_is_repository_level() {
+    let doc = r#"{"runs": [{"tool": {"driver": {"name": "Scorecard"}}, "results": [
+        {"ruleId": "BranchProtectionID", "level": "error",
+         "message": {"text": "branch protection not enabled"}}
+    ]}]}"#;
+    let findings = sarif::parse(doc, &options()).unwrap();
+    assert_eq!(findings.len(), 1);
+    assert!(findings[0].is_repository_level());
+    assert_eq!(findings[0].line, 0);
+    assert_eq!(findings[0].severity, Severity::Error);
+}
+
+// ── gitleaks ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_gitleaks_leaks_are_errors_without_the_secret() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("config.py"),
+        "DEBUG = True\nAPI_KEY = \"sk_live_abcdef1234567890\"\n",
+    )
+    .unwrap();
+    let report = r#"[
+      {"Description": "Stripe Access Token", "StartLine": 2, "EndLine": 2,
+       "StartColumn": 11, "EndColumn": 34, "Match": "sk_live_abcdef1234567890",
+       "Secret": "sk_live_abcdef1234567890", "File": "config.py", "Commit": "",
+       "RuleID": "stripe-access-token", "Tags": [], "Fingerprint": "config.py:stripe-access-token:2"},
+      {"Description": "", "StartLine": 7, "EndLine": 7, "StartColumn": 1, "EndColumn": 10,
+       "Secret": "hunter2", "File": "old/creds.txt", "Commit": "0123456789abcdef",
+       "RuleID": "generic-api-key"}
+    ]"#;
+    let findings = gitleaks::parse(report, &ImportOptions::new(dir.path())).unwrap();
+    assert_eq!(findings.len(), 2);
+
+    let stripe = &findings[0];
+    assert_eq!(stripe.severity, Severity::Error);
+    assert_eq!(
+        stripe.rule.as_deref(),
+        Some("EXT-gitleaks-stripe-access-token")
+    );
+    assert_eq!(stripe.message, "Stripe Access Token");
+    assert_eq!(stripe.file, dir.path().join("config.py"));
+    assert_eq!(
+        (stripe.line, stripe.column, stripe.end_column),
+        (2, Some(11), Some(35))
+    );
+    assert_eq!(stripe.secret_spans, vec![(11, 35)]);
+
+    let history = &findings[1];
+    assert_eq!(
+        history.message,
+        "Secret detected by gitleaks rule generic-api-key (commit 01234567)"
+    );
+    assert!(history.secret_spans.is_empty(), "file is gone");
+    for finding in &findings {
+        assert!(!finding.message.contains("sk_live"));
+        assert!(!finding.message.contains("hunter2"));
+    }
+
+    // A clean run writes `[]`, older versions `null`
+    assert!(gitleaks::parse("[]", &options()).unwrap().is_empty());
+    assert!(gitleaks::parse("null", &options()).unwrap().is_empty());
+    assert!(gitleaks::parse("{}", &options()).is_err());
+}
+
+// ── Semgrep ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_semgrep_json_results() {
+    let report = r#"{"results": [
+      {"check_id": "python.lang.security.audit.formatted-sql-query", "path": "./db/query.py",
+       "start": {"line": 8, "col": 9, "offset": 120}, "end": {"line": 8, "col": 40, "offset": 151},
+       "extra": {"message": "Possible SQL injection", "severity": "ERROR", "metadata": {}}},
+      {"check_id": "generic.todo", "path": "README.md",
+       "start": {"line": 1, "col": 1}, "end": {"line": 3, "col": 1},
+       "extra": {"message": "", "severity": "INFO"}},
+      {"check_id": "supply-chain.reachable", "path": "package.json",
+       "start": {"line": 4, "col": 3},
+       "extra": {"message": "Reachable vulnerable call", "severity": "MEDIUM"}}
+    ], "errors": []}"#;
+    let findings = semgrep::parse(report, &options()).unwrap();
+    let summary: Vec<_> = findings
+        .iter()
+        .map(|f| {
+            (
+                f.rule.as_deref().unwrap(),
+                f.severity,
+                f.line,
+                f.column,
+                f.end_column,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "EXT-semgrep-python.lang.security.audit.formatted-sql-query",
+                Severity::Error,
+                8,
+                Some(9),
+                Some(40)
+            ),
+            ("EXT-semgrep-generic.todo", Severity::Info, 1, Some(1), None),
+            (
+                "EXT-semgrep-supply-chain.reachable",
+                Severity::Warning,
+                4,
+                Some(3),
+                None
+            ),
+        ]
+    );
+    assert_eq!(findings[0].file, PathBuf::from("/repo/db/query.py"));
+    assert_eq!(findings[1].message, "generic.todo");
+    assert!(semgrep::parse("[]", &options()).is_err());
+}
+
+// ── Paths ─────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_paths_resolve_against_the_repository() {
+    let mut options = options();
+    assert_eq!(options.resolve("src/a.py"), PathBuf::from("/repo/src/a.py"));
+    assert_eq!(
+        options.resolve("./src/a.py"),
+        PathBuf::from("/repo/src/a.py")
+    );
+    assert_eq!(
+        options.resolve("src\\a.py"),
+        PathBuf::from("/repo/src/a.py")
+    );
+    assert_eq!(
+        options.resolve("/repo/src/a.py"),
+        PathBuf::from("/repo/src/a.py")
+    );
+    assert_eq!(
+        options.resolve("file:///repo/src/my%20file.py"),
+        PathBuf::from("/repo/src/my file.py")
+    );
+    // Outside the root and not stripped: kept, so it matches no file
+    assert_eq!(
+        options.resolve("/github/workspace/src/a.py"),
+        PathBuf::from("/github/workspace/src/a.py")
+    );
+
+    options.strip_prefix = Some("/github/workspace/".to_string());
+    assert_eq!(
+        options.resolve("/github/workspace/src/a.py"),
+        PathBuf::from("/repo/src/a.py")
+    );
+    assert_eq!(
+        options.resolve("file:///github/workspace/src/a.py"),
+        PathBuf::from("/repo/src/a.py")
+    );
+    // Only whole leading components are stripped
+    assert_eq!(
+        options.resolve("/github/workspace2/a.py"),
+        PathBuf::from("/github/workspace2/a.py")
+    );
+
+    options.strip_prefix = Some("services/api".to_string());
+    assert_eq!(
+        options.resolve("services/api/main.go"),
+        PathBuf::from("/repo/main.go")
+    );
+}
+
+#[test]
+fn test_load_reads_each_format() {
+    let dir = TempDir::new().unwrap();
+    let report = dir.path().join("leaks.json");
+    fs::write(
+        &report,
+        r#"[{"RuleID": "aws-access-token", "File": "a.py", "StartLine": 1}]"#,
+    )
+    .unwrap();
+    let findings = import::load(ImportFormat::Gitleaks, &report, &options()).unwrap();
+    assert_eq!(findings.len(), 1);
+
+    let err = import::load(ImportFormat::Semgrep, &report, &options()).unwrap_err();
+    assert!(format!("{:#}", err).contains("leaks.json"), "{:#}", err);
+    assert!(import::load(ImportFormat::Sarif, &dir.path().join("missing"), &options()).is_err());
+
+    assert_eq!(ImportFormat::parse("SARIF"), Some(ImportFormat::Sarif));
+    assert_eq!(ImportFormat::parse("trivy"), None);
+    assert_eq!(import::tool_name("Semgrep OSS"), "semgrep");
+}
+
+// ── Merge ─────────────────────────────────────────────────────────────────────
+
+fn native(id: &str, file: &str, line: usize) -> Finding {
+    Finding {
+        id: id.to_string(),
+        severity: Severity::Error,
+        message: id.to_string(),
+        file: PathBuf::from(file),
+        line,
+        ..Default::default()
+    }
+}
+
+fn external(tool: &str, rule: &str, file: &str, line: usize) -> Finding {
+    Finding {
+        rule: Some(import::rule(tool, rule)),
+        source_tool: Some(tool.to_string()),
+        ..native("EXT-000", file, line)
+    }
+}
+
+#[test]
+fn test_merge_drops_findings_revet_already_reported() {
+    let mut findings = vec![
+        native("SEC-001", "/repo/settings.py", 3),
+        native("SQL-001", "/repo/db.py", 10),
+    ];
+    let imported = vec![
+        // Same line as revet's secret finding
+        external("gitleaks", "aws-access-token", "/repo/settings.py", 3),
+        // Secret on another line
+        external("gitleaks", "aws-access-token", "/repo/settings.py", 4),
+        // Semgrep SQL rule on revet's SQL line; an unrelated rule there too
+        external("semgrep", "python.sqlalchemy.raw-sql", "/repo/db.py", 10),
+        external("semgrep", "python.lang.eval", "/repo/db.py", 10),
+        // The same rule reported twice
+        external("semgrep", "python.lang.eval", "/repo/db.py", 10),
+    ];
+    let dropped = import::merge(&mut findings, imported);
+    assert_eq!(dropped, 3);
+
+    let merged: Vec<(&str, &str, usize)> = findings
+        .iter()
+        .map(|f| (f.id.as_str(), f.rule.as_deref().unwrap_or(""), f.line))
+        .collect();
+    assert_eq!(
+        merged,
+        vec![
+            ("SEC-001", "", 3),
+            ("SQL-001", "", 10),
+            ("EXT-001", "EXT-gitleaks-aws-access-token", 4),
+            ("EXT-002", "EXT-semgrep-python.lang.eval", 10),
+        ]
+    );
+}
@@ -0,0 +1,386 @@
+//! Import classification: type-only, dev and test imports on the graph's
+//! `Imports` edges, and the BOUNDARY findings built on them.
+//!
+//! Each test writes a small project to a temp dir, parses it with
+//! `parse_files_parallel`, and checks the kinds the parser and resolver put
+//! on the file → import and file → file edges.
+
+use revet_core::config::RevetConfig;
+use revet_core::graph::{CodeGraph, EdgeKind, ImportKind, NodeData, NodeKind};
+use revet_core::{AnalyzerDispatcher, Finding, ParserDispatcher};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+fn write(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+    let path = dir.path().join(name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).unwrap();
+    }
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn parse(dir: &TempDir, files: &[PathBuf]) -> CodeGraph {
+    let (graph, errors) =
+        ParserDispatcher::new().parse_files_parallel(files, dir.path().to_path_buf());
+    assert!(errors.is_empty(), "errors: {:?}", errors);
+    graph
+}
+
+/// The kind of the file → import edge for each import, by module specifier
+fn import_kinds(graph: &CodeGraph) -> Vec<(String, ImportKind)> {
+    let mut kinds: Vec<(String, ImportKind)> = graph
+        .nodes()
+        .filter(|(_, n)| matches!(n.kind(), NodeKind::File))
+        .flat_map(|(id, _)| graph.edges_from(id))
+        .filter_map(|(target, edge)| match graph.node(target)?.data() {
+            NodeData::Import { module, .. } => Some((module.clone(), edge.import_kind())),
+            _ => None,
+        })
+        .collect();
+    kinds.sort_by(|a, b| a.0.cmp(&b.0));
+    kinds
+}
+
+/// The kinds of the file → file edges, as `(from, to, kind)` file names
+fn file_edges(graph: &CodeGraph) -> Vec<(String, String, ImportKind)> {
+    let name = |id| {
+        let node = graph.node(id).unwrap();
+        node.file_path()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string()
+    };
+    let mut edges: Vec<_> = graph
+        .nodes()
+        .filter(|(_, n)| matches!(n.kind(), NodeKind::File))
+        .flat_map(|(id, _)| graph.edges_from(id).map(move |(to, e)| (id, to, e)))
+        .filter(|(_, to, e)| {
+            matches!(e.kind(), EdgeKind::Imports)
+                && matches!(graph.node(*to).map(|n| n.kind()), Some(NodeKind::File))
+        })
+        .map(|(from, to, e)| (name(from), name(to), e.import_kind()))
+        .collect();
+    edges.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+    edges
+}
+
+fn boundary_findings(graph: &CodeGraph) -> Vec<Finding> {
+    let mut config = RevetConfig::default();
+    config.modules.dependency = true;
+    AnalyzerDispatcher::new()
+        .run_graph_analyzers(graph, &config)
+        .into_iter()
+        .filter(|f| f.id.starts_with("BOUNDARY"))
+        .collect()
+}
+
+// ── Type-only imports ────────────────────────────────────────────────────────
+
+#[test]
+fn test_typescript_type_only_imports() {
+    let dir = TempDir::new().unwrap();
+    let user = write(&dir, "user.ts", "export interface User { id: string }\n");
+    let app = write(
+        &dir,
+        "app.ts",
+        r#"import type { User } from "./user";
+import { type Role, type Scope } from "./roles";
+import { type Session, login } from "./session";
+import * as api from "./api";
+"#,
+    );
+    let graph = parse(&dir, &[user, app]);
+
+    assert_eq!(
+        import_kinds(&graph),
+        vec![
+            ("./api".to_string(), ImportKind::Runtime),
+            ("./roles".to_string(), ImportKind::TypeOnly),
+            ("./session".to_string(), ImportKind::Runtime),
+            ("./user".to_string(), ImportKind::TypeOnly),
+        ]
+    );
+    assert_eq!(
+        file_edges(&graph),
+        vec![(
+            "app.ts".to_string(),
+            "user.ts".to_string(),
+            ImportKind::TypeOnly
+        )]
+    );
+}
+
+#[test]
+fn test_python_type_checking_imports() {
+    let dir = TempDir::new().unwrap();
+    let models = write(&dir, "models.py", "class Order:\n    pass\n");
+    let service = write(
+        &dir,
+        "service.py",
+        r#"from typing import TYPE_CHECKING
+import typing
+
+if TYPE_CHECKING:
+    from models import Order
+
+if typing.TYPE_CHECKING:
+    import decimal
+
+import os
+"#,
+    );
+    let graph = parse(&dir, &[models, service]);
+
+    let kinds = import_kinds(&graph);
+    let kind = |module: &str| kinds.iter().find(|(m, _)| m == module).unwrap().1;
+    assert_eq!(kind("models"), ImportKind::TypeOnly);
+    assert_eq!(kind("decimal"), ImportKind::TypeOnly);
+    assert_eq!(kind("os"), ImportKind::Runtime);
+    assert_eq!(kind("typing"), ImportKind::Runtime);
+    assert_eq!(
+        file_edges(&graph),
+        vec![(
+            "service.py".to_string(),
+            "models.py".to_string(),
+            ImportKind::TypeOnly
+        )]
+    );
+}
+
+// ── Test imports ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_imports_of_test_code_flagged_from_production() {
+    let dir = TempDir::new().unwrap();
+    let helpers = write(&dir, "tests/helpers.py", "def make_user():\n    pass\n");
+    let app = write(
+        &dir,
+        "app.py",
+        "from tests.helpers import make_user\n\ndef run():\n    make_user()\n",
+    );
+    let test = write(
+        &dir,
+        "tests/test_app.py",
+        "from tests.helpers import make_user\n",
+    );
+    let graph = parse(&dir, &[helpers, app, test]);
+
+    assert_eq!(
+        file_edges(&graph),
+        vec![
+            (
+                "app.py".to_string(),
+                "helpers.py".to_string(),
+                ImportKind::Test
+            ),
+            (
+                "test_app.py".to_string(),
+                "helpers.py".to_string(),
+                ImportKind::Test
+            ),
+        ]
+    );
+
+    // Only the production file crosses the boundary
+    let findings = boundary_findings(&graph);
+    assert_eq!(findings.len(), 1, "{:?}", findings);
+    assert_eq!(findings[0].rule.as_deref(), Some("dependency:test_import"));
+    assert!(findings[0].file.ends_with("app.py"));
+    assert_eq!(findings[0].line, 1);
+    assert_eq!(
+        findings[0].message,
+        "Production code imports test code: `tests.helpers`"
+    );
+}
+
+#[test]
+fn test_type_only_import_of_test_code_is_not_flagged() {
+    let dir = TempDir::new().unwrap();
+    let fixtures = write(
+        &dir,
+        "__tests__/fixtures.ts",
+        "export interface Fixture { name: string }\n",
+    );
+    let app = write(
+        &dir,
+        "app.ts",
+        "import type { Fixture } from \"./__tests__/fixtures\";\n",
+    );
+    let graph = parse(&dir, &[fixtures, app]);
+
+    assert_eq!(
+        file_edges(&graph),
+        vec![(
+            "app.ts".to_string(),
+            "fixtures.ts".to_string(),
+            ImportKind::TypeOnly
+        )]
+    );
+    assert!(boundary_findings(&graph).is_empty());
+}
+
+// ── Dev dependencies ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_package_json_dev_dependencies() {
+    let dir = TempDir::new().unwrap();
+    write(
+        &dir,
+        "package.json",
+        r#"{
+  "dependencies": { "lodash": "^4.17.0", "@acme/ui": "1.0.0" },
+  "devDependencies": { "vitest": "^1.0.0", "@types/node": "^20.0.0", "lodash": "^4.17.0" }
+}"#,
+    );
+    let index = write(
+        &dir,
+        "src/index.ts",
+        r#"import { debounce } from "lodash";
+import { Button } from "@acme/ui/button";
+import { expect } from "vitest";
+import "./util";
+"#,
+    );
+    let util = write(&dir, "src/util.ts", "import { vi } from \"vitest\";\n");
+    let spec = write(
+        &dir,
+        "src/index.test.ts",
+        "import { describe } from \"vitest\";\n",
+    );
+    let graph = parse(&dir, &[index, util, spec]);
+
+    let kinds = import_kinds(&graph);
+    assert!(kinds.contains(&("lodash".to_string(), ImportKind::Runtime)));
+    assert!(kinds.contains(&("@acme/ui/button".to_string(), ImportKind::Runtime)));
+    assert_eq!(
+        kinds.iter().filter(|(_, k)| *k == ImportKind::Dev).count(),
+        3
+    );
+
+    // Production files, not the test file
+    let findings = boundary_findings(&graph);
+    let mut flagged: Vec<(String, Option<String>)> = findings
+        .iter()
+        .map(|f| {
+            (
+                f.file.file_name().unwrap().to_string_lossy().to_string(),
+                f.rule.clone(),
+            )
+        })
+        .collect();
+    flagged.sort();
+    assert_eq!(
+        flagged,
+        vec![
+            (
+                "index.ts".to_string(),
+                Some("dependency:dev_dependency".to_string())
+            ),
+            (
+                "util.ts".to_string(),
+                Some("dependency:dev_dependency".to_string())
+            ),
+        ]
+    );
+    assert!(findings[0]
+        .message
+        .ends_with("`vitest`, a development-only dependency"));
+}
+
+#[test]
+fn test_pyproject_dev_dependencies() {
+    let dir = TempDir::new().unwrap();
+    write(
+        &dir,
+        "pyproject.toml",
+        r#"[project]
+name = "shop"
+dependencies = ["requests>=2", "Flask_Login"]
+
+[dependency-groups]
+dev = ["pytest>=8", "Faker[extras]"]
+
+[tool.poetry.group.lint.dependencies]
+mypy = "^1.0"
+"#,
+    );
+    let app = write(
+        &dir,
+        "shop/app.py",
+        "import requests\nimport flask_login\nimport faker\nfrom mypy import api\n",
+    );
+    let graph = parse(&dir, &[app]);
+
+    let kinds = import_kinds(&graph);
+    let kind = |module: &str| kinds.iter().find(|(m, _)| m == module).unwrap().1;
+    assert_eq!(kind("requests"), ImportKind::Runtime);
+    assert_eq!(kind("flask_login"), ImportKind::Runtime);
+    assert_eq!(kind("faker"), ImportKind::Dev);
+    assert_eq!(kind("mypy"), ImportKind::Dev);
+    assert_eq!(boundary_findings(&graph).len(), 2);
+}
+
+#[test]
+fn test_cargo_dev_dependencies_use_the_nearest_manifest() {
+    let dir = TempDir::new().unwrap();
+    write(
+        &dir,
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"crates/app\"]\n\n[dev-dependencies]\nserde = \"1\"\n",
+    );
+    write(
+        &dir,
+        "crates/app/Cargo.toml",
+        r#"[package]
+name = "app"
+
+[dependencies]
+serde = "1"
+
+[dev-dependencies]
+pretty-assertions = "1"
+
+[target.'cfg(unix)'.dev-dependencies]
+tempfile = "3"
+"#,
+    );
+    let lib = write(
+        &dir,
+        "crates/app/src/lib.rs",
+        "use serde::Serialize;\nuse pretty_assertions::assert_eq;\nuse tempfile::TempDir;\nuse crate::config::Config;\n",
+    );
+    let graph = parse(&dir, &[lib]);
+
+    let kinds = import_kinds(&graph);
+    let dev: Vec<&str> = kinds
+        .iter()
+        .filter(|(_, k)| *k == ImportKind::Dev)
+        .map(|(m, _)| m.as_str())
+        .collect();
+    assert_eq!(dev.len(), 2, "{:?}", kinds);
+    assert!(dev.iter().any(|m| m.starts_with("pretty_assertions")));
+    assert!(dev.iter().any(|m| m.starts_with("tempfile")));
+}
+
+#[test]
+fn test_dev_imports_in_unreachable_scripts_are_not_flagged() {
+    let dir = TempDir::new().unwrap();
+    write(
+        &dir,
+        "package.json",
+        r#"{ "devDependencies": { "esbuild": "^0.20.0" } }"#,
+    );
+    let build = write(
+        &dir,
+        "scripts/build.js",
+        "import { build } from \"esbuild\";\n",
+    );
+    let graph = parse(&dir, &[build]);
+
+    assert!(import_kinds(&graph)
+        .iter()
+        .any(|(m, k)| m == "esbuild" && *k == ImportKind::Dev));
+    assert!(boundary_findings(&graph).is_empty());
+}
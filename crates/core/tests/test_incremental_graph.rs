@@ -0,0 +1,253 @@
+//! Tests for `IncrementalGraph`: after any sequence of edits, the updated
+//! graph must equal a full `parse_files_parallel` of the same files.
+
+use revet_core::graph::CodeGraph;
+use revet_core::{CancelToken, GraphUpdate, IncrementalGraph, ParserDispatcher};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+fn write(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+    let path = dir.path().join(name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).unwrap();
+    }
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+/// The graph's nodes and edges, independent of NodeIds and insertion order
+fn canonical(graph: &CodeGraph) -> (Vec<String>, Vec<String>) {
+    let describe = |id| format!("{:?}", graph.node(id).unwrap());
+    let mut nodes: Vec<String> = graph.nodes().map(|(id, _)| describe(id)).collect();
+    let mut edges: Vec<String> = graph
+        .nodes()
+        .flat_map(|(id, _)| {
+            graph
+                .edges_from(id)
+                .map(move |(target, edge)| {
+                    format!("{} -> {} {:?}", describe(id), describe(target), edge)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    nodes.sort();
+    edges.sort();
+    (nodes, edges)
+}
+
+fn assert_matches_full_parse(live: &IncrementalGraph, files: &[PathBuf], dir: &TempDir) {
+    let (full, errors) =
+        ParserDispatcher::new().parse_files_parallel(files, dir.path().to_path_buf());
+    assert_eq!(live.errors(), errors);
+    assert_eq!(canonical(live.graph()), canonical(&full));
+    assert!(live.graph().validate().is_ok());
+}
+
+#[test]
+fn test_updates_match_full_rebuild() {
+    let dir = TempDir::new().unwrap();
+    let files = vec![
+        write(
+            &dir,
+            "src/a.ts",
+            "export function foo() { return 1; }\nexport function bar() { return 2; }\n",
+        ),
+        write(
+            &dir,
+            "src/b.ts",
+            "import { foo, bar } from './a';\nexport function useFoo() { return foo() + bar(); }\n",
+        ),
+        write(
+            &dir,
+            "src/c.ts",
+            "import { useFoo } from './b';\nexport function main() { return useFoo(); }\n",
+        ),
+        write(&dir, "pkg/util.py", "def helper():\n    return 1\n"),
+        write(
+            &dir,
+            "pkg/app.py",
+            "from pkg.util import helper\n\ndef run():\n    return helper()\n",
+        ),
+    ];
+    let dispatcher = ParserDispatcher::new();
+    let token = CancelToken::new();
+    let mut live =
+        IncrementalGraph::build(&dispatcher, &files, dir.path().to_path_buf(), &token).unwrap();
+    assert_matches_full_parse(&live, &files, &dir);
+
+    let edits: &[(&[(&str, &str)], GraphUpdate)] = &[
+        // The imported symbol is renamed: b's references to it go away
+        (
+            &[(
+                "src/a.ts",
+                "export function foo2() { return 1; }\nexport function bar() { return 2; }\n",
+            )],
+            GraphUpdate::Incremental(1),
+        ),
+        // b follows the rename
+        (
+            &[(
+                "src/b.ts",
+                "import { foo2, bar } from './a';\nexport function useFoo() { return foo2() + bar(); }\n",
+            )],
+            GraphUpdate::Incremental(1),
+        ),
+        // Both ends of an import edited at once, lines shifted
+        (
+            &[
+                (
+                    "src/a.ts",
+                    "\n\nexport function foo2() { return 3; }\nexport function bar() { return 4; }\nexport function baz() { return 5; }\n",
+                ),
+                (
+                    "src/c.ts",
+                    "import { useFoo } from './b';\nimport { baz } from './a';\nexport function main() { return useFoo() + baz(); }\n",
+                ),
+            ],
+            GraphUpdate::Incremental(2),
+        ),
+        // A Python body edit keeps the incremental path
+        (
+            &[("pkg/util.py", "def helper():\n    return 2\n")],
+            GraphUpdate::Incremental(1),
+        ),
+        // A new Python function rebuilds
+        (
+            &[(
+                "pkg/util.py",
+                "def helper():\n    return 2\n\ndef other():\n    return 3\n",
+            )],
+            GraphUpdate::Rebuilt,
+        ),
+        // Back to the start
+        (
+            &[(
+                "src/a.ts",
+                "export function foo() { return 1; }\nexport function bar() { return 2; }\n",
+            )],
+            GraphUpdate::Incremental(1),
+        ),
+    ];
+    for (changes, expected) in edits {
+        for (name, content) in changes.iter() {
+            write(&dir, name, content);
+        }
+        let update = live.update(&dispatcher, &files, &token).unwrap();
+        assert_eq!(update, *expected, "after editing {:?}", changes);
+        assert_matches_full_parse(&live, &files, &dir);
+    }
+}
+
+#[test]
+fn test_unchanged_files_are_not_reparsed() {
+    let dir = TempDir::new().unwrap();
+    let files = vec![write(&dir, "a.ts", "export function foo() {}\n")];
+    let dispatcher = ParserDispatcher::new();
+    let token = CancelToken::new();
+    let mut live =
+        IncrementalGraph::build(&dispatcher, &files, dir.path().to_path_buf(), &token).unwrap();
+
+    let update = live.update(&dispatcher, &files, &token).unwrap();
+    assert_eq!(update, GraphUpdate::Unchanged);
+}
+
+#[test]
+fn test_added_and_removed_files_rebuild() {
+    let dir = TempDir::new().unwrap();
+    let a = write(
+        &dir,
+        "a.ts",
+        "import { bar } from './b';\nexport function foo() { return bar(); }\n",
+    );
+    let dispatcher = ParserDispatcher::new();
+    let token = CancelToken::new();
+    let mut live = IncrementalGraph::build(
+        &dispatcher,
+        std::slice::from_ref(&a),
+        dir.path().to_path_buf(),
+        &token,
+    )
+    .unwrap();
+
+    // b.ts appears: a's import resolves now
+    let b = write(&dir, "b.ts", "export function bar() { return 1; }\n");
+    let files = vec![a.clone(), b.clone()];
+    assert_eq!(
+        live.update(&dispatcher, &files, &token).unwrap(),
+        GraphUpdate::Rebuilt
+    );
+    assert_matches_full_parse(&live, &files, &dir);
+
+    std::fs::remove_file(&b).unwrap();
+    let files = vec![a];
+    assert_eq!(
+        live.update(&dispatcher, &files, &token).unwrap(),
+        GraphUpdate::Rebuilt
+    );
+    assert_matches_full_parse(&live, &files, &dir);
+}
+
+#[test]
+fn test_cancelled_update_keeps_the_graph() {
+    let dir = TempDir::new().unwrap();
+    let a = write(&dir, "a.ts", "export function foo() {}\n");
+    let files = vec![a];
+    let dispatcher = ParserDispatcher::new();
+    let mut live = IncrementalGraph::build(
+        &dispatcher,
+        &files,
+        dir.path().to_path_buf(),
+        &CancelToken::new(),
+    )
+    .unwrap();
+    let before = canonical(live.graph());
+
+    write(&dir, "a.ts", "export function renamed() {}\n");
+    let token = CancelToken::new();
+    token.cancel();
+    assert!(live.update(&dispatcher, &files, &token).is_err());
+    assert_eq!(canonical(live.graph()), before);
+
+    // The edit is still picked up by the next update
+    let update = live
+        .update(&dispatcher, &files, &CancelToken::new())
+        .unwrap();
+    assert_eq!(update, GraphUpdate::Incremental(1));
+    assert_matches_full_parse(&live, &files, &dir);
+}
+
+#[test]
+fn test_remove_file_maps_moved_nodes() {
+    let dir = TempDir::new().unwrap();
+    let a = write(
+        &dir,
+        "a.ts",
+        "export function foo() {}\nexport function bar() {}\n",
+    );
+    let b = write(
+        &dir,
+        "b.ts",
+        "import { foo } from './a';\nexport function baz() { foo(); }\n",
+    );
+    let c = write(&dir, "c.ts", "export function qux() {}\n");
+
+    let dispatcher = ParserDispatcher::new();
+    let (mut graph, _) =
+        dispatcher.parse_files_parallel(&[a.clone(), b, c], dir.path().to_path_buf());
+    let names_before: Vec<(_, String)> = graph
+        .nodes()
+        .filter(|(_, n)| n.file_path() != &a)
+        .map(|(id, n)| (id, n.name().to_string()))
+        .collect();
+
+    let moved = graph.remove_file(&a);
+    assert!(graph.nodes().all(|(_, n)| n.file_path() != &a));
+    assert!(graph.find_nodes(&a, None).is_empty());
+    assert!(graph.validate().is_ok());
+
+    // Every kept node is still reachable under its old ID, through the map
+    for (old, name) in names_before {
+        let now = moved.get(&old).copied().unwrap_or(old);
+        assert_eq!(graph.node(now).unwrap().name(), name);
+    }
+}
@@ -685,3 +685,47 @@ enum Status {
     println!("Node counts: {:?}", node_counts);
     println!("Edge counts: {:?}", edge_counts);
 }
+
+#[test]
+fn test_parse_annotations() {
+    let source = r#"
+@Service
+@org.springframework.transaction.annotation.Transactional(readOnly = true)
+public class OrderService {
+    @Transactional
+    @Override
+    public void place(Order order) {
+    }
+
+    @Inject
+    public OrderService(OrderRepository repo) {
+    }
+
+    public void plain() {
+    }
+}
+"#;
+
+    let graph = parse_java(source);
+    let decorators = |name: &str| -> Vec<String> {
+        graph
+            .nodes()
+            .find(|(_, n)| n.name() == name)
+            .map(|(_, n)| n.decorators().to_vec())
+            .unwrap_or_else(|| panic!("{} not found", name))
+    };
+
+    assert_eq!(
+        decorators("OrderService"),
+        vec![
+            "Service",
+            "org.springframework.transaction.annotation.Transactional"
+        ]
+    );
+    assert_eq!(
+        decorators("OrderService.place"),
+        vec!["Transactional", "Override"]
+    );
+    assert_eq!(decorators("OrderService.OrderService"), vec!["Inject"]);
+    assert!(decorators("OrderService.plain").is_empty());
+}
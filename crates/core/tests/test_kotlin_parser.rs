@@ -972,7 +972,7 @@ enum class Status {
 
     // 1 variable: version (name is a constructor param, not a property_declaration)
     assert!(
-        node_counts.get("Variable").is_some(),
+        node_counts.contains_key("Variable"),
         "Expected at least 1 variable"
     );
 
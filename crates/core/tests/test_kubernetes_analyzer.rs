@@ -0,0 +1,351 @@
+//! Integration tests for KubernetesAnalyzer and its YAML reader
+
+use revet_core::analyzer::kubernetes::yaml::{parse_documents, Value};
+use revet_core::analyzer::kubernetes::KubernetesAnalyzer;
+use revet_core::analyzer::{Analyzer, AnalyzerDispatcher};
+use revet_core::config::{KubernetesConfig, RevetConfig};
+use revet_core::finding::{Confidence, Finding, Severity};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn fixture(rel: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures/kubernetes_project")
+        .join(rel)
+}
+
+fn analyze(analyzer: &KubernetesAnalyzer, rel: &str) -> Vec<Finding> {
+    let path = fixture(rel);
+    analyzer.analyze_files(&[path], &fixture(""))
+}
+
+/// (line, severity, message fragment) for each finding, sorted
+fn summary(findings: &[Finding]) -> Vec<(usize, Severity, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .map(|f| (f.line, f.severity, f.message.clone()))
+        .collect();
+    rows.sort_by(|a, b| (a.0, &a.2).cmp(&(b.0, &b.2)));
+    rows
+}
+
+fn has(findings: &[Finding], line: usize, fragment: &str) -> bool {
+    findings
+        .iter()
+        .any(|f| f.line == line && f.message.contains(fragment))
+}
+
+fn rules(toml: &str) -> KubernetesConfig {
+    toml::from_str(toml).unwrap()
+}
+
+// ── Raw manifests ────────────────────────────────────────────────
+
+#[test]
+fn test_violating_manifest_every_rule_at_its_line() {
+    let findings = analyze(&KubernetesAnalyzer::new(), "manifests/violating.yaml");
+    let expected: &[(usize, Severity, &str)] = &[
+        (10, Severity::Error, "host network namespace"),
+        (11, Severity::Error, "host process namespace"),
+        (
+            13,
+            Severity::Warning,
+            "container `web` has no resources.limits",
+        ),
+        (
+            13,
+            Severity::Warning,
+            "container `web` in Deployment `api` has no readinessProbe or livenessProbe",
+        ),
+        (
+            14,
+            Severity::Warning,
+            "`nginx:latest` uses the mutable `latest` tag",
+        ),
+        (16, Severity::Error, "container `web` runs privileged"),
+        (
+            19,
+            Severity::Error,
+            "env var `DB_PASSWORD` in container `web`",
+        ),
+        (
+            22,
+            Severity::Warning,
+            "container `sidecar` in Deployment `api` has no livenessProbe",
+        ),
+        (
+            23,
+            Severity::Warning,
+            "image `busybox` has no tag or digest",
+        ),
+        (
+            24,
+            Severity::Warning,
+            "container `sidecar` has no resources.limits",
+        ),
+        (40, Severity::Error, "host IPC namespace"),
+        (
+            42,
+            Severity::Warning,
+            "container `report` has no resources.limits",
+        ),
+        (
+            43,
+            Severity::Warning,
+            "image `registry.example.com:5000/tools/report` has no tag or digest",
+        ),
+        (
+            45,
+            Severity::Error,
+            "env var `API_TOKEN` in container `report`",
+        ),
+    ];
+    for (line, severity, fragment) in expected {
+        assert!(
+            findings.iter().any(|f| f.line == *line
+                && f.severity == *severity
+                && f.message.contains(fragment)),
+            "missing {line}: {fragment}\ngot {:#?}",
+            summary(&findings)
+        );
+    }
+    assert_eq!(findings.len(), expected.len(), "{:#?}", summary(&findings));
+    assert!(findings.iter().all(|f| f.confidence == Confidence::High));
+}
+
+#[test]
+fn test_compliant_manifest_no_findings() {
+    let findings = analyze(&KubernetesAnalyzer::new(), "manifests/compliant.yaml");
+    assert!(findings.is_empty(), "{:#?}", summary(&findings));
+}
+
+#[test]
+fn test_secret_value_not_in_message() {
+    let findings = analyze(&KubernetesAnalyzer::new(), "manifests/violating.yaml");
+    assert!(findings.iter().all(|f| !f.message.contains("hunter2")));
+}
+
+#[test]
+fn test_privileged_has_replace_fix() {
+    let findings = analyze(&KubernetesAnalyzer::new(), "manifests/violating.yaml");
+    let privileged = findings
+        .iter()
+        .find(|f| f.message.contains("runs privileged"))
+        .unwrap();
+    assert!(matches!(
+        privileged.fix_kind,
+        Some(revet_core::finding::FixKind::ReplacePattern { .. })
+    ));
+}
+
+// ── Helm charts ──────────────────────────────────────────────────
+
+#[test]
+fn test_helm_values_defaults() {
+    let findings = analyze(&KubernetesAnalyzer::new(), "chart/values.yaml");
+    assert!(has(
+        &findings,
+        5,
+        "default image tag is the mutable `latest`"
+    ));
+    assert!(has(&findings, 10, "default enables privileged containers"));
+    assert!(has(
+        &findings,
+        12,
+        "default shares the host network namespace"
+    ));
+    assert!(has(&findings, 14, "default `resources` sets no limits"));
+    assert!(has(
+        &findings,
+        17,
+        "default image `busybox` has no tag or digest"
+    ));
+    assert_eq!(findings.len(), 5, "{:#?}", summary(&findings));
+    assert!(findings.iter().all(|f| f.confidence == Confidence::Medium));
+}
+
+#[test]
+fn test_values_yaml_outside_chart_is_a_plain_manifest() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("values.yaml");
+    std::fs::write(&path, "securityContext:\n  privileged: true\n").unwrap();
+    let findings = KubernetesAnalyzer::new().analyze_files(&[path], dir.path());
+    assert!(findings.is_empty());
+}
+
+#[test]
+fn test_helm_templated_values_not_flagged() {
+    let findings = analyze(
+        &KubernetesAnalyzer::new(),
+        "chart/templates/deployment.yaml",
+    );
+    assert!(findings.is_empty(), "{:#?}", summary(&findings));
+}
+
+#[test]
+fn test_helm_template_literals_still_checked() {
+    let findings = analyze(
+        &KubernetesAnalyzer::new(),
+        "chart/templates/migrate-job.yaml",
+    );
+    assert!(has(
+        &findings,
+        11,
+        "container `migrate` has no resources.limits"
+    ));
+    assert!(has(
+        &findings,
+        12,
+        "image `migrate/migrate` has no tag or digest"
+    ));
+    assert!(has(&findings, 14, "container `migrate` runs privileged"));
+    assert_eq!(findings.len(), 3, "{:#?}", summary(&findings));
+}
+
+// ── Rule switches ────────────────────────────────────────────────
+
+#[test]
+fn test_each_rule_can_be_switched_off() {
+    let all = analyze(&KubernetesAnalyzer::new(), "manifests/violating.yaml");
+    let cases = [
+        ("resource_limits", "resources.limits", 3),
+        ("privileged", "runs privileged", 1),
+        ("host_namespaces", "namespace", 3),
+        ("image_pinning", "image `", 3),
+        ("env_secrets", "env var", 2),
+        ("probes", "Probe", 2),
+    ];
+    for (rule, fragment, count) in cases {
+        let analyzer = KubernetesAnalyzer::with_rules(&rules(&format!("{rule} = false")));
+        let findings = analyze(&analyzer, "manifests/violating.yaml");
+        let dropped = all.len() - findings.len();
+        assert_eq!(dropped, count, "{rule}: {:#?}", summary(&findings));
+        assert!(
+            findings.iter().all(|f| !f.message.contains(fragment)),
+            "{rule} still reported"
+        );
+    }
+}
+
+#[test]
+fn test_helm_values_switch() {
+    let analyzer = KubernetesAnalyzer::with_rules(&rules("helm_values = false"));
+    assert!(analyze(&analyzer, "chart/values.yaml").is_empty());
+    assert!(!analyze(&analyzer, "chart/templates/migrate-job.yaml").is_empty());
+}
+
+#[test]
+fn test_rules_default_on() {
+    let config = RevetConfig::default();
+    assert!(!config.modules.kubernetes);
+    assert!(config.kubernetes.probes && config.kubernetes.helm_values);
+
+    let config: RevetConfig = toml::from_str("[kubernetes]\nprobes = false\n").unwrap();
+    assert!(!config.kubernetes.probes);
+    assert!(config.kubernetes.privileged);
+}
+
+// ── Dispatcher ───────────────────────────────────────────────────
+
+#[test]
+fn test_disabled_by_default() {
+    let config = RevetConfig::default();
+    assert!(!KubernetesAnalyzer::new().is_enabled(&config));
+
+    let mut config = RevetConfig::default();
+    config.modules.kubernetes = true;
+    assert!(KubernetesAnalyzer::new().is_enabled(&config));
+}
+
+#[test]
+fn test_replaces_infra_manifest_checks() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("pod.yaml");
+    std::fs::write(
+        &path,
+        "kind: Pod\nspec:\n  containers:\n  - name: app\n    image: app:latest\n    \
+         securityContext:\n      privileged: true\n  volumes:\n  - name: data\n    hostPath:\n      \
+         path: /var\n",
+    )
+    .unwrap();
+    let files = vec![path];
+
+    let mut config = RevetConfig::default();
+    config.modules.infra = true;
+    let infra_only =
+        AnalyzerDispatcher::new_with_config(&config).run_all(&files, dir.path(), &config);
+    assert!(infra_only
+        .iter()
+        .any(|f| f.id.starts_with("INFRA") && f.message.contains("privileged")));
+
+    config.modules.kubernetes = true;
+    let both = AnalyzerDispatcher::new_with_config(&config).run_all(&files, dir.path(), &config);
+    let privileged: Vec<_> = both
+        .iter()
+        .filter(|f| f.message.contains("privileged"))
+        .collect();
+    assert_eq!(privileged.len(), 1, "{:#?}", summary(&both));
+    assert!(privileged[0].id.starts_with("K8S-"));
+    assert!(
+        both.iter()
+            .any(|f| f.id.starts_with("INFRA") && f.message.contains("hostPath")),
+        "hostPath stays with INFRA"
+    );
+    assert!(!both
+        .iter()
+        .any(|f| f.id.starts_with("INFRA") && f.message.contains(":latest")));
+}
+
+// ── YAML reader ──────────────────────────────────────────────────
+
+#[test]
+fn test_yaml_positions_and_documents() {
+    let docs = parse_documents(
+        "# leading comment\nkind: Pod   # trailing\nspec:\n  containers:\n  - name: a\n    \
+         image: \"x:1\"\n  - name: b\n---\n\n---\nkind: Service\n",
+    );
+    assert_eq!(docs.len(), 2);
+
+    let pod = &docs[0];
+    assert_eq!(pod.get("kind").unwrap().as_str(), Some("Pod"));
+    assert_eq!(pod.entry("spec").unwrap().line, 3);
+    let containers = pod.path(&["spec", "containers"]).unwrap().items();
+    assert_eq!(containers.len(), 2);
+    assert_eq!(containers[0].line, 5);
+    assert_eq!(containers[0].entry("image").unwrap().line, 6);
+    assert_eq!(containers[0].get("image").unwrap().as_str(), Some("x:1"));
+    assert_eq!(containers[1].line, 7);
+
+    assert_eq!(docs[1].entry("kind").unwrap().line, 11);
+}
+
+#[test]
+fn test_yaml_scalars_and_flow() {
+    let docs = parse_documents(
+        "a: 'it''s'\nb: ~\nc: {x: 1, y: [p, \"q, r\"]}\nd: |\n  line one\n  # kept\ne: &anchor !!str v\nurl: http://h:80/p # c\n",
+    );
+    let doc = &docs[0];
+    assert_eq!(doc.get("a").unwrap().as_str(), Some("it's"));
+    assert_eq!(doc.get("b").unwrap().value, Value::Null);
+    assert_eq!(doc.path(&["c", "x"]).unwrap().as_str(), Some("1"));
+    let ys = doc.path(&["c", "y"]).unwrap().items();
+    assert_eq!(ys[1].as_str(), Some("q, r"));
+    assert_eq!(doc.get("d").unwrap().as_str(), Some("line one\n# kept"));
+    assert_eq!(doc.get("e").unwrap().as_str(), Some("v"));
+    assert_eq!(doc.entry("e").unwrap().line, 7);
+    assert_eq!(doc.get("url").unwrap().as_str(), Some("http://h:80/p"));
+}
+
+#[test]
+fn test_yaml_helm_templates_are_opaque() {
+    let docs = parse_documents(
+        "{{- if .Values.on }}\nspec:\n  resources:\n    {{- toYaml .Values.r | nindent 4 }}\n  \
+         image: \"{{ .Values.image }}\"\n  labels:\n  {{- include \"x\" . }}\n  name: n # {{ not a comment }}\n{{- end }}\n",
+    );
+    let spec = docs[0].get("spec").unwrap();
+    assert_eq!(spec.get("resources").unwrap().value, Value::Templated);
+    assert!(spec.get("image").unwrap().is_templated());
+    assert_eq!(spec.get("labels").unwrap().value, Value::Null);
+    assert_eq!(spec.entry("name").unwrap().line, 8);
+    assert_eq!(spec.get("name").unwrap().as_str(), Some("n"));
+}
@@ -0,0 +1,304 @@
+//! Tests for language version targets: resolution from manifests and
+//! `[targets]`, and the analyzer behavior that follows them
+
+use revet_core::analyzer::async_patterns::AsyncPatternsAnalyzer;
+use revet_core::analyzer::dependency::DependencyAnalyzer;
+use revet_core::analyzer::numeric::NumericAnalyzer;
+use revet_core::analyzer::Analyzer;
+use revet_core::config::RevetConfig;
+use revet_core::finding::Severity;
+use revet_core::{Language, LanguageTargets, ProfileSettings, TargetSource};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn write(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+    let path = dir.path().join(name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).unwrap();
+    }
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+/// `language`'s target version for `file`, and where it came from
+fn target(root: &Path, language: Language, file: &Path) -> (String, TargetSource) {
+    let none = BTreeMap::new();
+    let target = LanguageTargets::new(root, &none).for_file(language, file);
+    (target.version.to_string(), target.source)
+}
+
+fn manifest(dir: &TempDir, file: &str, key: &str) -> TargetSource {
+    TargetSource::Manifest {
+        file: dir.path().join(file),
+        key: key.to_string(),
+    }
+}
+
+#[test]
+fn test_manifest_declarations() {
+    let dir = TempDir::new().unwrap();
+    write(
+        &dir,
+        "web/tsconfig.base.json",
+        "{\n  // shared\n  \"compilerOptions\": { \"target\": \"ES2018\", },\n}\n",
+    );
+    write(
+        &dir,
+        "web/tsconfig.json",
+        r#"{ "extends": "./tsconfig.base", "include": ["src"] }"#,
+    );
+    write(
+        &dir,
+        "lib/jsconfig.json",
+        r#"{ "compilerOptions": { "lib": ["dom", "es2019.array", "ES2017"] } }"#,
+    );
+    write(
+        &dir,
+        "web/package.json",
+        r#"{ "engines": { "node": ">=14.17 <19" } }"#,
+    );
+    write(
+        &dir,
+        "svc/pyproject.toml",
+        "[tool.poetry.dependencies]\npython = \"^3.9\"\n",
+    );
+    write(
+        &dir,
+        "crate/Cargo.toml",
+        "[package]\nname = \"x\"\nedition = \"2018\"\n",
+    );
+    write(
+        &dir,
+        "legacy/pom.xml",
+        "<project><properties><java.version>1.8</java.version>\
+         <maven.compiler.source>${java.version}</maven.compiler.source></properties></project>",
+    );
+    write(
+        &dir,
+        "app/build.gradle.kts",
+        "java {\n    toolchain { languageVersion.set(JavaLanguageVersion.of(11)) }\n}\n",
+    );
+    let root = dir.path();
+
+    assert_eq!(
+        target(root, Language::EcmaScript, &root.join("web/src/deep/a.ts")),
+        (
+            "2018".to_string(),
+            manifest(&dir, "web/tsconfig.base.json", "compilerOptions.target")
+        )
+    );
+    assert_eq!(
+        target(root, Language::EcmaScript, &root.join("lib/a.js")),
+        (
+            "2019".to_string(),
+            manifest(&dir, "lib/jsconfig.json", "compilerOptions.lib")
+        )
+    );
+    assert_eq!(
+        target(root, Language::Node, &root.join("web/src/a.ts")),
+        (
+            "14.17".to_string(),
+            manifest(&dir, "web/package.json", "engines.node")
+        )
+    );
+    assert_eq!(
+        target(root, Language::Python, &root.join("svc/app/main.py")),
+        (
+            "3.9".to_string(),
+            manifest(
+                &dir,
+                "svc/pyproject.toml",
+                "tool.poetry.dependencies.python"
+            )
+        )
+    );
+    assert_eq!(
+        target(root, Language::Rust, &root.join("crate/src/lib.rs")),
+        (
+            "1.31".to_string(),
+            manifest(&dir, "crate/Cargo.toml", "package.edition")
+        )
+    );
+    assert_eq!(
+        target(root, Language::Java, &root.join("legacy/src/A.java")),
+        (
+            "8".to_string(),
+            manifest(&dir, "legacy/pom.xml", "maven.compiler.source")
+        )
+    );
+    assert_eq!(
+        target(root, Language::Java, &root.join("app/src/A.java")),
+        (
+            "11".to_string(),
+            manifest(&dir, "app/build.gradle.kts", "languageVersion")
+        )
+    );
+}
+
+#[test]
+fn test_defaults_and_unparsable_declarations() {
+    let dir = TempDir::new().unwrap();
+    write(
+        &dir,
+        "pyproject.toml",
+        "[project\nrequires-python = \">=3.8\"\n",
+    );
+    write(
+        &dir,
+        "package.json",
+        r#"{ "engines": { "node": "lts/*" } }"#,
+    );
+    let root = dir.path();
+    let file = root.join("main.py");
+
+    for (language, version) in [
+        (Language::Python, "3.12"),
+        (Language::Node, "20"),
+        (Language::EcmaScript, "2020"),
+    ] {
+        let (found, source) = target(root, language, &file);
+        assert_eq!(found, version, "{:?}", language);
+        assert!(matches!(source, TargetSource::Default { .. }));
+    }
+    assert_eq!(
+        target(root, Language::Python, &file).1,
+        TargetSource::Default {
+            unparsable: Some(root.join("pyproject.toml"))
+        }
+    );
+
+    // The reproducibility block records every language's resolution
+    let config = RevetConfig::default();
+    let reproducibility = ProfileSettings::resolve(None, &config)
+        .unwrap()
+        .reproducibility()
+        .with_language_targets(root, &config);
+    let records = &reproducibility.language_targets;
+    assert_eq!(records.len(), Language::ALL.len());
+    let python = records.iter().find(|r| r.language == "python").unwrap();
+    assert_eq!(python.source, "default");
+    assert_eq!(python.version, "3.12");
+    assert_eq!(python.manifest.as_deref(), Some("pyproject.toml"));
+}
+
+#[test]
+fn test_configured_targets_win() {
+    let dir = TempDir::new().unwrap();
+    write(
+        &dir,
+        "pyproject.toml",
+        "[project]\nrequires-python = \">=3.8\"\n",
+    );
+    let mut configured = BTreeMap::new();
+    configured.insert("python".to_string(), "3.13".to_string());
+    configured.insert("ecmascript".to_string(), "ES5".to_string());
+
+    let mut targets = LanguageTargets::new(dir.path(), &configured);
+    let python = targets.for_file(Language::Python, &dir.path().join("a.py"));
+    assert_eq!(python.version.to_string(), "3.13");
+    assert_eq!(python.source, TargetSource::Config);
+    assert!(!targets.for_repo(Language::EcmaScript).at_least("2015"));
+
+    let config: RevetConfig =
+        toml::from_str("[targets]\npython = \"three\"\ncobol = \"85\"\njava = \"1.8\"\n").unwrap();
+    let (errors, _) = config.validate();
+    let errors = errors.join("\n");
+    assert!(errors.contains("python = \"three\""), "{}", errors);
+    assert!(errors.contains("unknown language \"cobol\""), "{}", errors);
+    assert!(!errors.contains("java ="), "{}", errors);
+}
+
+#[test]
+fn test_bigint_suggestion_follows_the_ecmascript_target() {
+    let dir = TempDir::new().unwrap();
+    let file = write(
+        &dir,
+        "src/feed.ts",
+        "const tweetId = 1445078208190291968;\n",
+    );
+    let suggestion = || {
+        let findings =
+            NumericAnalyzer::new().analyze_files(std::slice::from_ref(&file), dir.path());
+        assert_eq!(findings.len(), 1);
+        findings[0].suggestion.clone().unwrap()
+    };
+
+    write(
+        &dir,
+        "tsconfig.json",
+        r#"{ "compilerOptions": { "target": "ES2020" } }"#,
+    );
+    assert!(suggestion().contains("1445078208190291968n"));
+
+    // Only the manifest changes
+    write(
+        &dir,
+        "tsconfig.json",
+        r#"{ "compilerOptions": { "target": "ES2018" } }"#,
+    );
+    let legacy = suggestion();
+    assert!(!legacy.contains("BigInt literal ("), "{}", legacy);
+    assert!(legacy.contains("as a string"), "{}", legacy);
+}
+
+#[test]
+fn test_deprecated_module_rule_follows_the_python_target() {
+    let dir = TempDir::new().unwrap();
+    let file = write(&dir, "tool/setup_helpers.py", "import distutils\n");
+    let findings =
+        || DependencyAnalyzer::new().analyze_files(std::slice::from_ref(&file), dir.path());
+
+    // Nothing declared: the default target has the modules removed
+    assert_eq!(findings().len(), 1);
+
+    write(
+        &dir,
+        "pyproject.toml",
+        "[project]\nrequires-python = \">=3.11\"\n",
+    );
+    assert!(findings().is_empty());
+
+    write(
+        &dir,
+        "pyproject.toml",
+        "[project]\nrequires-python = \">=3.12\"\n",
+    );
+    let found = findings();
+    assert_eq!(found.len(), 1);
+    assert!(found[0].message.contains("deprecated Python module import"));
+
+    // `[targets]` overrides the manifest
+    let mut configured = BTreeMap::new();
+    configured.insert("python".to_string(), "3.10".to_string());
+    let analyzer = DependencyAnalyzer::new().with_targets(&configured);
+    assert!(analyzer
+        .analyze_files(std::slice::from_ref(&file), dir.path())
+        .is_empty());
+}
+
+#[test]
+fn test_unhandled_then_follows_the_node_and_ecmascript_targets() {
+    let dir = TempDir::new().unwrap();
+    let file = write(&dir, "src/api.js", "fetch(url).then((r) => r.json());\n");
+    let finding = || {
+        let findings =
+            AsyncPatternsAnalyzer::new().analyze_files(std::slice::from_ref(&file), dir.path());
+        assert_eq!(findings.len(), 1);
+        findings.into_iter().next().unwrap()
+    };
+
+    let modern = finding();
+    assert_eq!(modern.severity, Severity::Warning);
+    assert!(modern.suggestion.unwrap().contains("async/await"));
+
+    write(&dir, "package.json", r#"{ "engines": { "node": ">=12" } }"#);
+    write(
+        &dir,
+        "jsconfig.json",
+        r#"{ "compilerOptions": { "target": "es2015" } }"#,
+    );
+    let legacy = finding();
+    assert_eq!(legacy.severity, Severity::Info);
+    assert!(!legacy.suggestion.unwrap().contains("await"));
+}
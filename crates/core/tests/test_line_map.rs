@@ -0,0 +1,219 @@
+//! Tests for reconciling line numbers after `apply_fixes`: findings and
+//! baseline anchors must keep pointing at the same source text
+
+mod common;
+
+use proptest::prelude::*;
+use revet_core::finding::{Finding, FixKind};
+use revet_core::fixer::{apply_fixes, plan_fixes};
+use revet_core::{Anchor, Baseline, LineMap, Severity};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Unique token at the start of line `i`, which no fix touches
+fn token(i: usize) -> String {
+    format!("l{:03}_", i)
+}
+
+fn source(lines: usize) -> Vec<String> {
+    (1..=lines)
+        .map(|i| format!("{} = compute({})", token(i), i))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Edit {
+    /// Delete the whole line
+    Remove,
+    /// Insert a FIXME comment above it
+    CommentOut,
+    /// Split the line after the token
+    Split,
+    /// Rewrite text after the token on the same line
+    Rewrite,
+}
+
+fn fix_kind(edit: Edit, line: &str) -> FixKind {
+    match edit {
+        Edit::Remove => FixKind::RemoveStatement {
+            statement: line.to_string(),
+        },
+        Edit::CommentOut => FixKind::CommentOut,
+        Edit::Split => FixKind::ReplacePattern {
+            find: "compute".to_string(),
+            replace: "comp\nute".to_string(),
+        },
+        Edit::Rewrite => FixKind::ReplacePattern {
+            find: "compute".to_string(),
+            replace: "calculate".to_string(),
+        },
+    }
+}
+
+fn finding(file: &Path, line: usize, id: &str, fix_kind: Option<FixKind>) -> Finding {
+    Finding {
+        id: id.to_string(),
+        severity: Severity::Warning,
+        message: format!("finding on {}", token(line)),
+        file: file.to_path_buf(),
+        line,
+        suggestion: Some("Review this line".to_string()),
+        fix_kind,
+        ..Default::default()
+    }
+}
+
+/// Apply `edits` (line, kind) to a file of `lines` lines, then reconcile a
+/// suggestion-only finding and a baseline entry on every line
+fn fix_and_reconcile(lines: usize, edits: &[(usize, Edit)]) {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("app.py");
+    let original = source(lines);
+    std::fs::write(&file, original.join("\n") + "\n").unwrap();
+
+    let mut findings: Vec<Finding> = edits
+        .iter()
+        .map(|&(line, edit)| {
+            let kind = fix_kind(edit, &original[line - 1]);
+            finding(&file, line, &format!("FIX-{:03}", line), Some(kind))
+        })
+        .collect();
+    let anchored: Vec<Finding> = (1..=lines)
+        .map(|line| finding(&file, line, &format!("OTHER-{:03}", line), None))
+        .collect();
+    findings.extend(anchored.iter().cloned());
+    let mut baseline = Baseline::from_findings(&anchored, dir.path(), None);
+
+    let log = TempDir::new().unwrap();
+    let mut report = apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
+    let removed: Vec<usize> = edits
+        .iter()
+        .filter(|(_, e)| matches!(e, Edit::Remove))
+        .map(|&(line, _)| line)
+        .collect();
+
+    let fixed: Vec<String> = std::fs::read_to_string(&file)
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect();
+    let points_at = |line: usize, original: usize| fixed[line - 1].contains(&token(original));
+
+    let survivors = report.reconcile(anchored);
+    assert_eq!(survivors.len(), lines - removed.len());
+    for finding in &survivors {
+        let before: usize = finding.id["OTHER-".len()..].parse().unwrap();
+        assert!(
+            points_at(finding.line, before),
+            "{} moved to line {}: {:?}",
+            finding.id,
+            finding.line,
+            fixed
+        );
+    }
+
+    baseline.reconcile(&mut report, dir.path());
+    assert_eq!(baseline.count, lines - removed.len());
+    for entry in &baseline.entries {
+        let before: usize = entry.message["finding on l".len()..][..3].parse().unwrap();
+        assert!(!removed.contains(&before));
+        assert!(points_at(entry.line.unwrap(), before), "{:?}", entry);
+    }
+    let mut dropped_entries: Vec<usize> = report
+        .dropped
+        .iter()
+        .filter(|d| matches!(d.anchor, Anchor::BaselineEntry(_)))
+        .map(|d| d.line)
+        .collect();
+    dropped_entries.sort_unstable();
+    assert_eq!(dropped_entries, removed);
+}
+
+fn edit() -> impl Strategy<Value = Edit> {
+    prop_oneof![
+        Just(Edit::Remove),
+        Just(Edit::CommentOut),
+        Just(Edit::Split),
+        Just(Edit::Rewrite),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn prop_reconciled_lines_keep_their_text(
+        lines in 1usize..30,
+        picks in prop::collection::btree_map(0usize..30, edit(), 0..12),
+    ) {
+        // One edit per line, so the set never overlaps
+        let edits: Vec<(usize, Edit)> = picks
+            .into_iter()
+            .filter(|(i, _)| *i < lines)
+            .map(|(i, e)| (i + 1, e))
+            .collect();
+        fix_and_reconcile(lines, &edits);
+    }
+}
+
+#[test]
+fn test_line_map_from_edit_spans() {
+    let content = source(6).join("\n");
+    let path = Path::new("app.py");
+    let findings = [
+        finding(path, 2, "A", Some(fix_kind(Edit::Remove, &source(6)[1]))),
+        finding(path, 4, "B", Some(FixKind::CommentOut)),
+        finding(path, 5, "C", Some(fix_kind(Edit::Split, ""))),
+    ];
+    let map = LineMap::from_plans(&plan_fixes(&findings, &content));
+
+    assert_eq!(map.map(1), Some(1));
+    assert_eq!(map.map(2), None);
+    assert!(map.is_deleted(2));
+    assert_eq!(map.map(3), Some(2));
+    // The comment goes above line 4, whose text moves below it
+    assert_eq!(map.map(4), Some(4));
+    // The split keeps the start of line 5 in place
+    assert_eq!(map.map(5), Some(5));
+    assert_eq!(map.map(6), Some(7));
+    assert!(!map.is_identity());
+
+    let rewrite = [finding(path, 1, "D", Some(fix_kind(Edit::Rewrite, "")))];
+    assert!(LineMap::from_plans(&plan_fixes(&rewrite, &content)).is_identity());
+}
+
+#[test]
+fn test_stale_baseline_entry_on_a_deleted_line_is_dropped() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("app.py");
+    std::fs::write(
+        &file,
+        "def run(cursor, uid):\n    breakpoint()\n    cursor.execute(q + uid)\n",
+    )
+    .unwrap();
+    let debugger = finding(
+        &file,
+        2,
+        "DEBUG-001",
+        Some(FixKind::RemoveStatement {
+            statement: "breakpoint()".to_string(),
+        }),
+    );
+    let query = finding(&file, 3, "SQL-001", None);
+    let mut baseline =
+        Baseline::from_findings(&[debugger.clone(), query.clone()], dir.path(), None);
+
+    let log = TempDir::new().unwrap();
+    let mut report = apply_fixes(&[debugger], &mut common::journal(log.path())).unwrap();
+    assert_eq!(report.reconcile(vec![query])[0].line, 2);
+
+    assert!(baseline.reconcile(&mut report, dir.path()));
+    assert_eq!(baseline.entries.len(), 1);
+    assert_eq!(baseline.entries[0].line, Some(2));
+    assert_eq!(report.dropped.len(), 1);
+    assert_eq!(report.dropped[0].line, 2);
+    assert_eq!(
+        report.dropped[0].anchor,
+        Anchor::BaselineEntry("finding on l002_".to_string())
+    );
+}
@@ -0,0 +1,275 @@
+//! Tests for the LSP conversion layer (`revet fixes --format lsp-json`)
+
+mod common;
+
+use revet_core::fixer::{apply_fixes, plan_fixes};
+use revet_core::lsp::{self, LineIndex, LspFix, Position, Range, TextEdit};
+use revet_core::{AnalyzerDispatcher, Finding, FixKind, RevetConfig, Severity};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn finding(id: &str, file: &Path, line: usize, fix_kind: FixKind) -> Finding {
+    Finding {
+        id: id.to_string(),
+        severity: Severity::Warning,
+        message: format!("finding {}", id),
+        file: file.to_path_buf(),
+        line,
+        suggestion: Some("Fix it".to_string()),
+        fix_kind: Some(fix_kind),
+        ..Default::default()
+    }
+}
+
+fn replace(find: &str, replace: &str) -> FixKind {
+    FixKind::ReplacePattern {
+        find: find.to_string(),
+        replace: replace.to_string(),
+    }
+}
+
+fn pos(line: u32, character: u32) -> Position {
+    Position { line, character }
+}
+
+/// Apply LSP text edits to `content` by UTF-16 offsets — deliberately not
+/// sharing any code with the fixer
+fn apply_lsp_edits(content: &str, edits: &[TextEdit]) -> String {
+    let units: Vec<u16> = content.encode_utf16().collect();
+    let mut line_starts = vec![0usize];
+    for (i, unit) in units.iter().enumerate() {
+        if *unit == u16::from(b'\n') {
+            line_starts.push(i + 1);
+        }
+    }
+    let offset = |p: &Position| line_starts[p.line as usize] + p.character as usize;
+
+    let mut sorted: Vec<&TextEdit> = edits.iter().collect();
+    sorted.sort_by_key(|e| (e.range.start, e.range.end));
+    let mut result = units;
+    for edit in sorted.iter().rev() {
+        let start = offset(&edit.range.start);
+        let end = offset(&edit.range.end);
+        result.splice(start..end, edit.new_text.encode_utf16());
+    }
+    String::from_utf16(&result).unwrap()
+}
+
+/// Every code action's edits for `uri`, checked to be sorted and disjoint
+fn all_edits(fixes: &[LspFix], uri: &str) -> Vec<TextEdit> {
+    let mut edits: Vec<TextEdit> = fixes
+        .iter()
+        .filter_map(|f| f.code_action.as_ref())
+        .flat_map(|a| a.edit.changes[uri].clone())
+        .collect();
+    edits.sort_by_key(|e| (e.range.start, e.range.end));
+    for pair in edits.windows(2) {
+        assert!(
+            pair[0].range.end <= pair[1].range.start
+                && (pair[0].range.start != pair[0].range.end
+                    || pair[0].range.start != pair[1].range.start),
+            "overlapping edits: {pair:?}"
+        );
+    }
+    edits
+}
+
+/// `--fix` output for `findings` on a copy of `content`
+fn fixed_by_apply_fixes(root: &Path, name: &str, content: &str, findings: &[Finding]) -> String {
+    let copy = root.join(format!("fix-{}", name));
+    std::fs::write(&copy, content).unwrap();
+    let findings: Vec<Finding> = findings
+        .iter()
+        .map(|f| Finding {
+            file: copy.clone(),
+            ..f.clone()
+        })
+        .collect();
+    let log = TempDir::new().unwrap();
+    apply_fixes(&findings, &mut common::journal(log.path())).unwrap();
+    std::fs::read_to_string(&copy).unwrap()
+}
+
+fn assert_round_trip(root: &Path, name: &str, content: &str, findings: &[Finding]) {
+    let path = root.join(name);
+    std::fs::write(&path, content).unwrap();
+    let fixes = lsp::fixes_for_document(findings, &path, content);
+    let edits = all_edits(&fixes, &lsp::file_uri(&path));
+    assert!(!edits.is_empty());
+
+    assert_eq!(
+        apply_lsp_edits(content, &edits),
+        fixed_by_apply_fixes(root, name, content, findings)
+    );
+}
+
+// ── Positions ────────────────────────────────────────────────────
+
+#[test]
+fn test_positions_count_utf16_units() {
+    // 'é' is one UTF-16 unit, '🎉' two
+    let index = LineIndex::new("plain\né🎉x = 1\n");
+    assert_eq!(index.position(1, 3), pos(0, 2));
+    assert_eq!(index.position(2, 2), pos(1, 1));
+    assert_eq!(index.position(2, 3), pos(1, 3));
+    assert_eq!(index.position(2, 4), pos(1, 4));
+    assert_eq!(index.line_end(2), pos(1, 8));
+    // Past the end clamps
+    assert_eq!(index.position(9, 1), pos(1, 0));
+}
+
+#[test]
+fn test_finding_range_uses_columns() {
+    let content = "log.info(f\"🎉 {user.password}\")\n";
+    let index = LineIndex::new(content);
+    let mut f = finding("LOG-001", Path::new("/r/a.py"), 1, FixKind::Suggestion);
+    f.column = Some(14);
+    f.end_column = Some(27);
+    // Columns 14..27 are characters; the emoji before them adds one unit
+    assert_eq!(
+        index.finding_range(&f),
+        Range {
+            start: pos(0, 14),
+            end: pos(0, 27)
+        }
+    );
+
+    f.column = None;
+    f.end_column = None;
+    assert_eq!(index.finding_range(&f).end, pos(0, 31));
+}
+
+#[test]
+fn test_diagnostic_fields() {
+    let path = PathBuf::from("/r/lib.rs");
+    let mut f = finding("ERR-002", &path, 1, FixKind::Suggestion);
+    f.severity = Severity::Error;
+    f.rule = Some("error_handling:unwrap".to_string());
+    let fixes = lsp::fixes_for_document(&[f.clone()], &path, "x.unwrap();\n");
+
+    assert_eq!(fixes.len(), 1);
+    assert_eq!(fixes[0].diagnostic.severity, 1);
+    assert_eq!(fixes[0].diagnostic.code, "error_handling:unwrap");
+    assert_eq!(fixes[0].diagnostic.source, "revet");
+    assert!(fixes[0].code_action.is_none(), "suggestion-only");
+
+    f.rule = None;
+    let fixes = lsp::fixes_for_document(&[f], &path, "x.unwrap();\n");
+    assert_eq!(fixes[0].diagnostic.code, "ERR");
+}
+
+#[test]
+fn test_json_shape() {
+    let path = PathBuf::from("/r/x.tf");
+    let f = finding("INFRA-001", &path, 1, replace("public-read", "private"));
+    let fixes = lsp::fixes_for_document(&[f], &path, "acl = \"public-read\"\n");
+    let json = serde_json::to_value(&fixes).unwrap();
+
+    let action = &json[0]["codeAction"];
+    assert_eq!(action["kind"], "quickfix");
+    assert_eq!(action["isPreferred"], true);
+    let edit = &action["edit"]["changes"]["file:///r/x.tf"][0];
+    assert_eq!(edit["newText"], "private");
+    assert_eq!(edit["range"]["start"]["character"], 7);
+    assert_eq!(edit["range"]["end"]["character"], 18);
+}
+
+#[test]
+fn test_file_uri_percent_encodes() {
+    assert_eq!(
+        lsp::file_uri(Path::new("/repo/my app/ü.ts")),
+        "file:///repo/my%20app/%C3%BC.ts"
+    );
+}
+
+// ── Round trip against --fix ─────────────────────────────────────
+
+#[test]
+fn test_round_trip_matches_fix_output() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    let content = "# 🎉 settings\n\
+                   API_KEY = 'AKIA1234567890123456'\n\
+                   bucket_acl = \"public-read\"  # ünïcode before: public-read\n\
+                   import pickle\n\
+                   other = 1\n";
+    let path = root.join("app.py");
+    let findings = vec![
+        finding("SEC-001", &path, 2, FixKind::CommentOut),
+        finding(
+            "INFRA-001",
+            &path,
+            3,
+            replace(r#""public-read""#, r#""private""#),
+        ),
+        finding(
+            "ML-001",
+            &path,
+            4,
+            replace(r"import (pickle)", "import joblib  # was $1"),
+        ),
+        // No longer matches: no edit, like --fix
+        finding("ML-002", &path, 5, replace("absent", "x")),
+    ];
+
+    assert_round_trip(&root, "app.py", content, &findings);
+}
+
+#[test]
+fn test_overlapping_fixes_keep_the_first() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    let content = "privileged: true\nprivileged: true\n";
+    let path = root.join("pod.yaml");
+    let findings = vec![
+        finding(
+            "K8S-001",
+            &path,
+            1,
+            replace(r"privileged:\s*true", "privileged: false"),
+        ),
+        finding("INFRA-001", &path, 1, replace(r"true", "false")),
+        finding("K8S-002", &path, 2, FixKind::CommentOut),
+        finding("INFRA-002", &path, 2, FixKind::CommentOut),
+    ];
+
+    let plans = plan_fixes(&findings, content);
+    let ids: Vec<&str> = plans.iter().map(|p| p.finding_id.as_str()).collect();
+    assert_eq!(ids, vec!["K8S-001", "K8S-002"]);
+
+    let fixes = lsp::fixes_for_document(&findings, &path, content);
+    assert_eq!(fixes.len(), 4, "every finding keeps its diagnostic");
+    assert_eq!(fixes.iter().filter(|f| f.code_action.is_some()).count(), 2);
+
+    assert_round_trip(&root, "pod.yaml", content, &findings);
+}
+
+#[test]
+fn test_round_trip_with_analyzer_findings() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    let content = "apiVersion: v1\n\
+                   kind: Pod\n\
+                   metadata:\n  name: débug\n\
+                   spec:\n\
+                   \x20 containers:\n\
+                   \x20   - name: app\n\
+                   \x20     image: nginx:1.25.3\n\
+                   \x20     securityContext:\n\
+                   \x20       privileged: true\n";
+    let path = root.join("pod.yaml");
+    std::fs::write(&path, content).unwrap();
+
+    let mut config = RevetConfig::default();
+    config.modules.kubernetes = true;
+    let findings = AnalyzerDispatcher::new_with_config(&config).run_all(
+        std::slice::from_ref(&path),
+        &root,
+        &config,
+    );
+    assert!(findings
+        .iter()
+        .any(|f| matches!(f.fix_kind, Some(FixKind::ReplacePattern { .. }))));
+
+    assert_round_trip(&root, "pod.yaml", content, &findings);
+}
@@ -95,3 +95,225 @@ fn test_finding_metadata() {
     assert!(f.suggestion.is_some());
     assert!(f.message.contains("42"));
 }
+
+// ── Configuration-sensitive call sites (tunables) ────────────────────────────
+
+fn run_tunables(file: &str, source: &str) -> Vec<revet_core::Finding> {
+    run_tunables_with(MagicNumbersAnalyzer::new(), file, source)
+}
+
+fn run_tunables_with(
+    analyzer: MagicNumbersAnalyzer,
+    file: &str,
+    source: &str,
+) -> Vec<revet_core::Finding> {
+    analyzer
+        .analyze_source(
+            std::path::Path::new(file),
+            source,
+            std::path::Path::new("/repo"),
+        )
+        .into_iter()
+        .filter(|f| f.message.starts_with("Hardcoded"))
+        .collect()
+}
+
+#[test]
+fn test_tunable_python_keyword_timeout() {
+    let findings = run_tunables(
+        "app/client.py",
+        "import requests\n\ndef fetch(url):\n    return requests.get(url, timeout=30)\n",
+    );
+    assert_eq!(findings.len(), 1, "{:?}", findings);
+    assert_eq!(findings[0].line, 4);
+    assert_eq!(findings[0].severity, revet_core::Severity::Info);
+    assert!(findings[0].message.contains("timeout=30"));
+    assert!(findings[0].message.contains("`get` call"));
+}
+
+#[test]
+fn test_tunable_python_retry_and_pool() {
+    let source = "\
+from urllib3.util import Retry
+from sqlalchemy import create_engine
+
+retry = Retry(total=5, backoff_factor=0.5)
+engine = create_engine(DB_URL, pool_size=20, max_overflow=10)
+";
+    let findings = run_tunables("app/db.py", source);
+    assert_eq!(findings.len(), 4, "{:?}", findings);
+    assert!(findings
+        .iter()
+        .all(|f| f.severity == revet_core::Severity::Info));
+}
+
+#[test]
+fn test_tunable_python_positional_not_flagged() {
+    let source = "\
+import time
+
+def wait():
+    time.sleep(30)
+    return fetch(url, 30)
+";
+    assert!(run_tunables("app/wait.py", source).is_empty());
+}
+
+#[test]
+fn test_tunable_python_near_misses() {
+    let source = "\
+import os
+from django.conf import settings
+
+TIMEOUT = int(os.environ.get(\"HTTP_TIMEOUT\", \"30\"))
+PAGE_SIZE = 50
+
+def fetch(url):
+    retries = 3
+    requests.get(url, timeout=settings.TIMEOUT)
+    paginate(url, page_size=50)
+";
+    let findings = run_tunables("app/near.py", source);
+    assert!(findings.is_empty(), "{:?}", findings);
+}
+
+#[test]
+fn test_tunable_python_module_constants() {
+    let source = "\
+REQUEST_TIMEOUT = 30
+MAX_RETRIES = 5
+CACHE_TTL = 3600
+DEFAULT_NAME = 42
+";
+    let findings = run_tunables("app/settings.py", source);
+    let lines: Vec<usize> = findings.iter().map(|f| f.line).collect();
+    assert_eq!(lines, vec![1, 2, 3], "{:?}", findings);
+    assert!(findings[1].message.contains("MAX_RETRIES"));
+}
+
+#[test]
+fn test_tunable_escalates_outside_bounds() {
+    let source = "\
+MAX_RETRIES = 50
+requests.post(url, timeout=600)
+requests.post(url, timeout=120)
+engine = create_engine(DB_URL, pool_size=5000)
+";
+    let findings = run_tunables("app/bounds.py", source);
+    let warnings: Vec<usize> = findings
+        .iter()
+        .filter(|f| f.severity == revet_core::Severity::Warning)
+        .map(|f| f.line)
+        .collect();
+    assert_eq!(warnings, vec![1, 2, 4], "{:?}", findings);
+    assert!(findings[1]
+        .message
+        .contains("600s exceeds the sanity bound of 300s"));
+}
+
+#[test]
+fn test_tunable_typescript_options_object() {
+    let source = "\
+import axios from 'axios';
+import { Pool } from 'pg';
+
+const client = axios.create({ baseURL: API, timeout: 5000 });
+const pool = new Pool({ max: 20, idleTimeoutMillis: 30000 });
+";
+    let findings = run_tunables("src/client.ts", source);
+    let lines: Vec<usize> = findings.iter().map(|f| f.line).collect();
+    assert_eq!(lines, vec![4, 5, 5], "{:?}", findings);
+    assert!(findings[0].message.contains("timeout: 5000"));
+}
+
+#[test]
+fn test_tunable_typescript_milliseconds_bound() {
+    // 600000ms = 600s, over the 300s timeout bound
+    let findings = run_tunables("src/slow.ts", "fetchWithRetry(url, { timeout: 600000 });\n");
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].severity, revet_core::Severity::Warning);
+}
+
+#[test]
+fn test_tunable_typescript_near_misses() {
+    let source = "\
+setTimeout(cb, 5000);
+const limits = pick({ max: 20 });
+const client = axios.create({ timeout: Number(process.env.HTTP_TIMEOUT_MS) });
+function build() {
+  const REQUEST_TIMEOUT = 3000;
+}
+";
+    let findings = run_tunables("src/near.ts", source);
+    assert!(findings.is_empty(), "{:?}", findings);
+}
+
+#[test]
+fn test_tunable_typescript_module_constants() {
+    let source = "\
+export const REQUEST_TIMEOUT_MS = 30000;
+const MAX_RETRY_COUNT = 3;
+let retryDelay = 100;
+";
+    let findings = run_tunables("src/config.ts", source);
+    let lines: Vec<usize> = findings.iter().map(|f| f.line).collect();
+    assert_eq!(lines, vec![1, 2], "{:?}", findings);
+}
+
+#[test]
+fn test_tunable_skips_test_files() {
+    let source = "requests.get(url, timeout=1)\n";
+    assert!(run_tunables("tests/test_client.py", source).is_empty());
+    assert!(run_tunables("src/client.test.ts", "get(u, { timeout: 1 });\n").is_empty());
+}
+
+#[test]
+fn test_tunable_generic_scan_not_duplicated() {
+    let analyzer = MagicNumbersAnalyzer::new();
+    let findings = analyzer.analyze_source(
+        std::path::Path::new("app/client.py"),
+        "requests.get(url, timeout=30)\n",
+        std::path::Path::new("/repo"),
+    );
+    assert_eq!(findings.len(), 1, "{:?}", findings);
+    assert!(findings[0].message.starts_with("Hardcoded"));
+}
+
+#[test]
+fn test_tunable_config_pattern() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[[magic_numbers.patterns]]
+language = "python"
+function = "connect"
+parameter = "wait_secs"
+kind = "timeout"
+max = 60
+"#,
+    )
+    .unwrap();
+    let analyzer = MagicNumbersAnalyzer::with_patterns(&config.magic_numbers.patterns);
+    let source = "\
+broker.connect(host, wait_secs=90)
+other.open(host, wait_secs=90)
+";
+    let findings = run_tunables_with(analyzer, "app/broker.py", source);
+    assert_eq!(findings.len(), 1, "{:?}", findings);
+    assert_eq!(findings[0].severity, revet_core::Severity::Warning);
+}
+
+#[test]
+fn test_tunable_config_validation() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[[magic_numbers.patterns]]
+parameter = "wait"
+kind = "latency"
+unit = "fortnights"
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    assert!(errors.iter().any(|e| e.contains("kind = \"latency\"")));
+    assert!(errors.iter().any(|e| e.contains("unit = \"fortnights\"")));
+}
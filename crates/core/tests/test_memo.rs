@@ -0,0 +1,189 @@
+//! Integration tests for the in-memory analyzer memo of watch and daemon
+//! sessions (`[performance] memo_mb`)
+
+use revet_core::config::RevetConfig;
+use revet_core::{AnalysisMemo, AnalyzerDispatcher, Finding, ParserDispatcher};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// `n` Python files, each with a SQL injection, a magic number and a
+/// hardcoded secret
+fn repo(n: usize) -> (TempDir, Vec<PathBuf>) {
+    let dir = TempDir::new().unwrap();
+    let files = (0..n)
+        .map(|i| {
+            let path = dir.path().join(format!("m{:02}.py", i));
+            std::fs::write(&path, source(i, 1)).unwrap();
+            path
+        })
+        .collect();
+    (dir, files)
+}
+
+fn source(i: usize, queries: usize) -> String {
+    format!(
+        "password = \"hunter2-Secret-Value\"\n\ndef handler_{i}(uid):\n    timeout = 86400\n{}",
+        "    cursor.execute(f\"SELECT * FROM t WHERE id = {uid}\")\n".repeat(queries)
+    )
+}
+
+fn json(findings: &[Finding]) -> serde_json::Value {
+    serde_json::to_value(findings).unwrap()
+}
+
+/// Findings of a dispatcher without a memo
+fn cold(files: &[PathBuf], root: &Path, config: &RevetConfig) -> Vec<Finding> {
+    AnalyzerDispatcher::new_with_config(config).run_all_parallel(files, root, config)
+}
+
+#[test]
+fn test_unchanged_files_are_not_analyzed_again() {
+    let (dir, files) = repo(5);
+    let config = RevetConfig::default();
+    let memo = AnalysisMemo::new(64 << 20);
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config).with_memo(Some(memo.clone()));
+
+    let first = dispatcher.run_all_parallel(&files, dir.path(), &config);
+    let cold_run = memo.stats();
+    assert_eq!(cold_run.hits, 0);
+    assert!(cold_run.misses > 0);
+    assert_eq!(cold_run.misses % files.len(), 0);
+    let per_file_analyzers = cold_run.misses / files.len();
+    assert_eq!(json(&first), json(&cold(&files, dir.path(), &config)));
+
+    // Nothing changed: every per-file analyzer call is answered
+    let second = dispatcher.run_all_parallel(&files, dir.path(), &config);
+    let run = memo.stats().since(&cold_run);
+    assert_eq!(run.misses, 0);
+    assert_eq!(run.hits, per_file_analyzers * files.len());
+    assert_eq!(json(&second), json(&first));
+
+    // One file changed: only it is analyzed again, and the output is a
+    // cold run's
+    for (cycle, changed) in [2, 0, 4].into_iter().enumerate() {
+        std::fs::write(&files[changed], source(changed, cycle + 2)).unwrap();
+        let before = memo.stats();
+        let findings = dispatcher.run_all_parallel(&files, dir.path(), &config);
+        let run = memo.stats().since(&before);
+        assert_eq!(run.misses, per_file_analyzers, "cycle {cycle}");
+        assert_eq!(run.hits, per_file_analyzers * (files.len() - 1));
+        assert_eq!(run.invalidations, per_file_analyzers);
+        assert_eq!(json(&findings), json(&cold(&files, dir.path(), &config)));
+    }
+}
+
+#[test]
+fn test_partitioned_run_uses_the_memo() {
+    let (dir, files) = repo(4);
+    let config = RevetConfig::default();
+    let memo = AnalysisMemo::new(64 << 20);
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config).with_memo(Some(memo.clone()));
+    let partitions = revet_core::analyzer::partition::partition_by(&files, dir.path(), 2);
+
+    dispatcher.run_all_parallel(&files, dir.path(), &config);
+    let before = memo.stats();
+    let (findings, _) =
+        dispatcher.run_all_partitioned_timed(&files, &partitions, dir.path(), &config, |_, _| {});
+    let run = memo.stats().since(&before);
+    assert_eq!(run.misses, 0);
+    assert!(run.hits > 0);
+    assert_eq!(json(&findings), json(&cold(&files, dir.path(), &config)));
+}
+
+#[test]
+fn test_config_change_invalidates_everything() {
+    let (dir, files) = repo(3);
+    let memo = AnalysisMemo::new(64 << 20);
+    let config = RevetConfig::default();
+    AnalyzerDispatcher::new_with_config(&config)
+        .with_memo(Some(memo.clone()))
+        .run_all_parallel(&files, dir.path(), &config);
+    let entries = memo.stats().entries;
+    assert!(entries > 0);
+
+    let mut edited = RevetConfig::default();
+    edited.modules.magic_numbers = !edited.modules.magic_numbers;
+    let before = memo.stats();
+    let findings = AnalyzerDispatcher::new_with_config(&edited)
+        .with_memo(Some(memo.clone()))
+        .run_all_parallel(&files, dir.path(), &edited);
+    let run = memo.stats().since(&before);
+    assert_eq!(run.hits, 0);
+    assert_eq!(run.invalidations, entries);
+    assert_eq!(json(&findings), json(&cold(&files, dir.path(), &edited)));
+}
+
+#[test]
+fn test_least_recently_used_are_evicted() {
+    let (dir, files) = repo(6);
+    let config = RevetConfig::default();
+    // Room for a few entries only
+    let memo = AnalysisMemo::new(8 << 10);
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config).with_memo(Some(memo.clone()));
+
+    let findings = dispatcher.run_all_parallel(&files, dir.path(), &config);
+    let stats = memo.stats();
+    assert!(stats.evictions > 0);
+    assert!(stats.bytes <= 8 << 10);
+    assert_eq!(json(&findings), json(&cold(&files, dir.path(), &config)));
+
+    memo.resize(0);
+    assert_eq!(memo.stats().entries, 0);
+}
+
+#[test]
+fn test_graph_analyzers_memoized_per_file_set() {
+    let (dir, files) = repo(3);
+    let config = RevetConfig::default();
+    let memo = AnalysisMemo::new(64 << 20);
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config).with_memo(Some(memo.clone()));
+    let parse = || {
+        ParserDispatcher::new_with_config(&config)
+            .parse_files_parallel(&files, dir.path().to_path_buf())
+            .0
+    };
+
+    let graph = parse();
+    let first = dispatcher.run_graph_analyzers(&graph, &config);
+    let before = memo.stats();
+    let second = dispatcher.run_graph_analyzers(&graph, &config);
+    let run = memo.stats().since(&before);
+    assert_eq!(run.misses, 0);
+    assert!(run.hits > 0);
+    assert_eq!(json(&second), json(&first));
+
+    // A changed file gives the set a new fingerprint
+    std::fs::write(&files[1], source(1, 3)).unwrap();
+    let graph = parse();
+    let before = memo.stats();
+    let findings = dispatcher.run_graph_analyzers(&graph, &config);
+    assert_eq!(memo.stats().since(&before).hits, 0);
+    assert_eq!(
+        json(&findings),
+        json(&AnalyzerDispatcher::new_with_config(&config).run_graph_analyzers(&graph, &config))
+    );
+}
+
+#[test]
+fn test_buffers_memoized_per_content() {
+    let (dir, files) = repo(1);
+    let config = RevetConfig::default();
+    let memo = AnalysisMemo::new(64 << 20);
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config).with_memo(Some(memo.clone()));
+    let content = source(0, 2);
+
+    let first = dispatcher.run_buffer(&files[0], &content, None, dir.path(), &config);
+    let before = memo.stats();
+    let second = dispatcher.run_buffer(&files[0], &content, None, dir.path(), &config);
+    let run = memo.stats().since(&before);
+    assert_eq!(run.misses, 0);
+    assert!(run.hits > 0);
+    assert_eq!(json(&second), json(&first));
+
+    // An edit replaces the buffer's entries
+    let before = memo.stats();
+    dispatcher.run_buffer(&files[0], &source(0, 3), None, dir.path(), &config);
+    let run = memo.stats().since(&before);
+    assert_eq!(run.hits, 0);
+    assert_eq!(run.invalidations, run.misses);
+}
@@ -0,0 +1,117 @@
+//! Memory guard: shedding order, the stop over the ceiling and sampling
+//! between checkpoints
+
+use revet_core::memory::{resident_bytes, MemoryGuard, Shedding};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const MB: u64 = 1024 * 1024;
+
+/// A guard with a 100 MB ceiling reading `usage`
+fn guard(usage: &Arc<AtomicU64>) -> MemoryGuard {
+    let usage = usage.clone();
+    MemoryGuard::with_probe(100, move || Some(usage.load(Ordering::SeqCst)))
+}
+
+#[test]
+fn test_steps_are_taken_in_order() {
+    let usage = Arc::new(AtomicU64::new(50 * MB));
+    let mut guard = guard(&usage);
+    assert!(guard.is_enforced());
+    assert_eq!(guard.high_water(), 85 * MB);
+
+    let mut asked = Vec::new();
+    assert!(!guard.checkpoint("domain analyzers", |step| {
+        asked.push(step);
+        Some(String::new())
+    }));
+    assert!(asked.is_empty());
+
+    // Past the high-water mark: one step per checkpoint
+    usage.store(90 * MB, Ordering::SeqCst);
+    assert!(!guard.checkpoint("domain analyzers", |step| {
+        asked.push(step);
+        Some("partitioned".to_string())
+    }));
+    // Steps with nothing to act on fall through to the next one
+    assert!(!guard.checkpoint("graph analyzers", |step| {
+        asked.push(step);
+        (step == Shedding::SkipAnalyzers).then(|| "dead code analyzer".to_string())
+    }));
+    assert_eq!(
+        asked,
+        [
+            Shedding::SmallerBatches,
+            Shedding::DropCaches,
+            Shedding::LazyGraph,
+            Shedding::SkipAnalyzers
+        ]
+    );
+    let shed: Vec<(Shedding, bool)> = guard.shed().iter().map(|s| (s.step, s.applied)).collect();
+    assert_eq!(
+        shed,
+        [
+            (Shedding::SmallerBatches, true),
+            (Shedding::DropCaches, false),
+            (Shedding::LazyGraph, false),
+            (Shedding::SkipAnalyzers, true),
+        ]
+    );
+    assert_eq!(
+        guard.shed()[1].to_string(),
+        "in-memory caches dropped before graph analyzers at 90 MB: nothing cached in memory (unavailable)"
+    );
+
+    // Under the ceiling, analyzers keep being skipped
+    asked.clear();
+    assert!(!guard.checkpoint("scans", |step| {
+        asked.push(step);
+        Some("hotspot ranking".to_string())
+    }));
+    assert_eq!(asked, [Shedding::SkipAnalyzers]);
+    assert!(!guard.is_stopped());
+
+    // Over it, with every step taken, the review stops
+    usage.store(120 * MB, Ordering::SeqCst);
+    assert!(guard.checkpoint("scans", |_| panic!("nothing left to shed")));
+    assert!(guard.checkpoint("output", |_| None));
+    let report = guard.report();
+    assert!(report.exceeded);
+    assert_eq!(report.stopped_before.as_deref(), Some("scans"));
+    assert_eq!(report.max_memory_mb, 100);
+    assert_eq!(report.peak_mb, 120);
+    assert_eq!(report.shed.len(), 5);
+}
+
+#[test]
+fn test_stops_when_nothing_can_be_shed() {
+    let usage = Arc::new(AtomicU64::new(150 * MB));
+    let mut guard = guard(&usage);
+    assert!(guard.checkpoint("domain analyzers", |_| None));
+    assert_eq!(guard.shed().len(), 4);
+    assert!(guard.shed().iter().all(|s| !s.applied));
+}
+
+#[test]
+fn test_peak_between_checkpoints_counts() {
+    let usage = Arc::new(AtomicU64::new(10 * MB));
+    let mut guard = guard(&usage);
+    usage.store(95 * MB, Ordering::SeqCst);
+    std::thread::sleep(Duration::from_millis(300));
+    usage.store(10 * MB, Ordering::SeqCst);
+    assert!(!guard.checkpoint("graph analyzers", |_| Some(String::new())));
+    assert_eq!(guard.shed().len(), 1);
+    assert_eq!(guard.shed()[0].rss_mb, 95);
+}
+
+#[test]
+fn test_unreadable_memory_is_not_enforced() {
+    let mut guard = MemoryGuard::with_probe(1, || None);
+    assert!(!guard.is_enforced());
+    assert!(!guard.checkpoint("domain analyzers", |_| panic!("not enforced")));
+    assert_eq!(guard.report().peak_mb, 0);
+
+    #[cfg(target_os = "linux")]
+    assert!(resident_bytes().unwrap() > 0);
+}
@@ -0,0 +1,351 @@
+//! Integration tests for NumericAnalyzer
+
+mod common;
+
+use revet_core::analyzer::numeric::{Names, NumericAnalyzer};
+use revet_core::analyzer::{Analyzer, AnalyzerDispatcher};
+use revet_core::config::{NumericConfig, RevetConfig};
+use revet_core::finding::{Confidence, Finding, Severity};
+use std::path::Path;
+use tempfile::TempDir;
+
+const FIXTURE: &str = "numeric";
+const EXTENSIONS: &[&str] = &[".py", ".ts", ".js", ".java", ".go", ".rs", ".cs"];
+
+/// (repo-relative file, line, sub-rule) for each NUM finding, sorted
+fn summary(findings: &[Finding], root: &Path) -> Vec<(String, usize, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("NUM"))
+        .map(|f| {
+            let file = f.file.strip_prefix(root).unwrap_or(&f.file);
+            (
+                file.to_string_lossy().replace('\\', "/"),
+                f.line,
+                f.rule.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+fn row(file: &str, line: usize, rule: &str) -> (String, usize, String) {
+    (file.to_string(), line, format!("numeric:{}", rule))
+}
+
+fn analyze_with(config: &NumericConfig, file: &str, content: &str) -> Vec<Finding> {
+    let tmp = TempDir::new().unwrap();
+    NumericAnalyzer::with_config(config).analyze_source(&tmp.path().join(file), content, tmp.path())
+}
+
+fn analyze(file: &str, content: &str) -> Vec<Finding> {
+    analyze_with(&NumericConfig::default(), file, content)
+}
+
+/// (line, sub-rule) pairs
+fn lines(findings: &[Finding]) -> Vec<(usize, String)> {
+    findings
+        .iter()
+        .map(|f| (f.line, f.rule.clone().unwrap_or_default()))
+        .collect()
+}
+
+fn at(line: usize, rule: &str) -> (usize, String) {
+    (line, rule.to_string())
+}
+
+#[test]
+fn test_fixture_findings() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let expected = vec![
+        row("billing/invoice.py", 4, "money_float"),
+        row("billing/invoice.py", 9, "money_float"),
+        row("billing/invoice.py", 11, "money_float"),
+        row("billing/invoice.py", 13, "money_float"),
+        row("billing/invoice.py", 15, "float_equality"),
+        row("cs/Invoice.cs", 3, "money_float"),
+        row("cs/Invoice.cs", 9, "money_float"),
+        row("go/invoice.go", 4, "money_float"),
+        row("go/invoice.go", 8, "money_float"),
+        row("go/invoice.go", 9, "float_equality"),
+        row("java/Invoice.java", 4, "money_float"),
+        row("java/Invoice.java", 7, "money_float"),
+        row("java/Invoice.java", 12, "float_equality"),
+        row("rust/invoice.rs", 2, "money_float"),
+        row("rust/invoice.rs", 7, "money_float"),
+        row("web/api.js", 3, "unsafe_integer_id"),
+        row("web/api.js", 4, "unsafe_integer_id"),
+        row("web/api.js", 7, "unsafe_integer_id"),
+        row("web/api.js", 8, "unsafe_integer_id"),
+        row("web/cart.ts", 4, "money_float"),
+        row("web/cart.ts", 7, "money_float"),
+        row("web/cart.ts", 9, "float_equality"),
+    ];
+    assert_eq!(summary(&findings, &root), expected);
+}
+
+#[test]
+fn test_correct_versions_pass() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    for clean in [
+        "billing/ledger.py",
+        "web/prices.ts",
+        "web/api_safe.js",
+        "tests/test_invoice.py",
+    ] {
+        assert!(
+            findings.iter().all(|f| f.file != root.join(clean)),
+            "{} has findings",
+            clean
+        );
+    }
+}
+
+#[test]
+fn test_confidence_and_default_severities() {
+    let (_, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let of = |rule: &str| -> Vec<&Finding> {
+        findings
+            .iter()
+            .filter(|f| f.rule.as_deref() == Some(rule))
+            .collect()
+    };
+    assert!(of("numeric:money_float")
+        .iter()
+        .all(|f| f.severity == Severity::Warning && f.confidence == Confidence::High));
+    assert!(of("numeric:float_equality")
+        .iter()
+        .all(|f| f.severity == Severity::Info && f.confidence == Confidence::Medium));
+    let ids: Vec<Confidence> = of("numeric:unsafe_integer_id")
+        .iter()
+        .map(|f| f.confidence)
+        .collect();
+    assert_eq!(
+        ids,
+        vec![
+            Confidence::High,
+            Confidence::High,
+            Confidence::Medium,
+            Confidence::Medium
+        ]
+    );
+}
+
+#[test]
+fn test_suggestion_names_the_language_decimal_type() {
+    let cases = [
+        ("a.py", "price: float = 1.5\n", "decimal.Decimal"),
+        ("a.ts", "const price = 1.5;\n", "decimal.js"),
+        ("A.java", "class A { double price; }\n", "BigDecimal"),
+        (
+            "a.go",
+            "package a\nvar price float64\n",
+            "shopspring/decimal",
+        ),
+        ("a.rs", "struct A { price: f64 }\n", "rust_decimal"),
+        ("A.cs", "class A { double price; }\n", "`decimal`"),
+    ];
+    for (file, source, expected) in cases {
+        let findings = analyze(file, source);
+        assert_eq!(findings.len(), 1, "{}", file);
+        let suggestion = findings[0].suggestion.as_deref().unwrap();
+        assert!(suggestion.contains(expected), "{}: {}", file, suggestion);
+    }
+}
+
+#[test]
+fn test_money_names_match_trailing_words() {
+    let names = Names::new(&["price".to_string(), "line_total".to_string()]);
+    for name in [
+        "price",
+        "unitPrice",
+        "unit_price",
+        "UNIT_PRICE",
+        "prices",
+        "$price",
+    ] {
+        assert!(names.matches(name), "{}", name);
+    }
+    assert!(names.matches("orderLineTotal"));
+    for name in ["price_ratio", "priced", "total", "spice", "lineTotals2"] {
+        assert!(!names.matches(name), "{}", name);
+    }
+}
+
+#[test]
+fn test_float_types_and_values() {
+    let findings = analyze(
+        "billing.py",
+        "\
+price = 9.99
+amount = float(raw)
+fee = -0.5
+total = 10
+balance: Decimal = Decimal('1.00')
+cost: Optional[float] = None
+",
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![
+            at(1, "money_float"),
+            at(2, "money_float"),
+            at(3, "money_float"),
+            at(6, "money_float")
+        ]
+    );
+
+    // TypeScript's `number` alone says nothing about floats
+    let findings = analyze(
+        "cart.ts",
+        "let price: number = 0;\nlet total = 100;\nconst amount = Number.parseFloat(s);\n",
+    );
+    assert_eq!(lines(&findings), vec![at(3, "money_float")]);
+}
+
+#[test]
+fn test_float_equality_needs_a_float_side() {
+    let findings = analyze(
+        "calc.py",
+        "\
+ratio: float = compute()
+if ratio == 1:
+    pass
+if count == 3:
+    pass
+if score != 0.5:
+    pass
+if name == other:
+    pass
+",
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![at(2, "float_equality"), at(6, "float_equality")]
+    );
+    assert!(findings[0].message.contains("ratio == 1"));
+
+    // Ordering comparisons and integer literals are fine
+    assert!(analyze(
+        "calc.go",
+        "package a\nfunc f(x int) bool { return x == 3 || x < 0.5 }\n"
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_unsafe_ids() {
+    let findings = analyze(
+        "feed.ts",
+        "\
+const tweetId = 1445078208190291968;
+const userId = 123456789012345;
+const orderId = 10000000000000000000;
+const postId = 1445078208190291968n;
+this.id = 1445078208190291968;
+const ids = JSON.parse(body);
+const { user: { id }, count } = JSON.parse(body);
+const parsed = JSON.parse(body);
+",
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![
+            at(1, "unsafe_integer_id"),
+            at(5, "unsafe_integer_id"),
+            at(6, "unsafe_integer_id"),
+            at(7, "unsafe_integer_id")
+        ]
+    );
+    assert!(findings[0]
+        .suggestion
+        .as_deref()
+        .unwrap()
+        .contains("1445078208190291968n"));
+
+    // Only JavaScript numbers lose digits
+    assert!(analyze("feed.py", "tweet_id = 1445078208190291968\n").is_empty());
+}
+
+#[test]
+fn test_configured_names() {
+    let config: NumericConfig =
+        toml::from_str("money_names = [\"payout\"]\nid_names = [\"snowflake\"]\n").unwrap();
+    let findings = analyze_with(
+        &config,
+        "a.js",
+        "let price = 1.5;\nlet weeklyPayout = 2.5;\nconst id = 1445078208190291968;\nconst guildSnowflake = 1445078208190291968;\n",
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![at(2, "money_float"), at(4, "unsafe_integer_id")]
+    );
+}
+
+#[test]
+fn test_tests_are_skipped_unless_included() {
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("tests/test_cart.py");
+    let source = "price: float = 1.5\n";
+    assert!(NumericAnalyzer::new()
+        .analyze_source(&file, source, tmp.path())
+        .is_empty());
+
+    let config: NumericConfig = toml::from_str("include_tests = true\n").unwrap();
+    assert_eq!(
+        NumericAnalyzer::with_config(&config)
+            .analyze_source(&file, source, tmp.path())
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_severity_override_and_selection() {
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("a.py");
+    std::fs::write(&file, "price = 1.5\nif price == 2.0:\n    pass\n").unwrap();
+    let config: RevetConfig = toml::from_str(
+        "[modules]\nnumeric = true\n\n[analyzers.numeric]\ndisable = [\"float_equality\"]\n\n[analyzers.numeric.severity]\nmoney_float = \"error\"\n",
+    )
+    .unwrap();
+    let findings =
+        AnalyzerDispatcher::new_with_config(&config).run_all(&[file], tmp.path(), &config);
+    let rules: Vec<_> = findings
+        .iter()
+        .map(|f| (f.rule.as_deref().unwrap_or_default(), f.severity))
+        .collect();
+    assert_eq!(rules, vec![("numeric:money_float", Severity::Error)]);
+}
+
+#[test]
+fn test_config_validation() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[analyzers.numeric]
+money_names = ["price", ""]
+only = ["money"]
+
+[analyzers.numeric.severity]
+money_float = "loud"
+ids = "info"
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    let errors: Vec<&String> = errors.iter().filter(|e| e.contains("numeric")).collect();
+    assert_eq!(errors.len(), 4, "{:?}", errors);
+    assert!(errors.iter().any(|e| e.contains("money_names")));
+    assert!(errors.iter().any(|e| e.contains("\"money\"")));
+    assert!(errors.iter().any(|e| e.contains("money_float = \"loud\"")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("unknown sub-rule \"ids\"")));
+
+    assert!(RevetConfig::default()
+        .validate()
+        .0
+        .iter()
+        .all(|e| !e.contains("numeric")));
+}
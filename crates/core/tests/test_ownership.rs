@@ -0,0 +1,422 @@
+//! Ownership analysis: blame of a scripted git history with three authors.
+//!
+//! Alice still works on the project, Bob last committed on day 300 and
+//! Carol on day 10; HEAD is on day 395. With the roster naming only Alice,
+//! or with the 180-day window (which keeps Alice and Bob active):
+//!
+//! | file                 | lines by             | roster | window |
+//! |----------------------|----------------------|--------|--------|
+//! | `src/core/engine.py` | Carol 32             | orphan | orphan |
+//! | `src/core/util.py`   | Alice 20             |        |        |
+//! | `src/api/handler.py` | Carol 20, Alice 5    |        |        |
+//! | `src/api/routes.py`  | Bob 20               | orphan |        |
+//! | `vendor/lib.py`      | Carol 20 (CODEOWNERS)|        |        |
+//! | `tiny.py`            | Carol 3 (too small)  |        |        |
+
+mod common;
+
+use common::{commit, python_fn};
+use git2::Repository;
+use revet_core::analyzer::ownership::codeowners::CodeOwners;
+use revet_core::analyzer::ownership::{display, OwnershipReport};
+use revet_core::config::RevetConfig;
+use revet_core::graph::{CodeGraph, Node, NodeData, NodeKind};
+use revet_core::{AnalyzerDispatcher, GitObjects, Severity};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const ALICE: (&str, &str) = ("Alice", "alice@example.com");
+const BOB: (&str, &str) = ("Bob", "Bob@Example.com");
+const CAROL: (&str, &str) = ("Carol", "carol@example.com");
+
+const FILES: &[&str] = &[
+    "src/core/engine.py",
+    "src/core/util.py",
+    "src/api/handler.py",
+    "src/api/routes.py",
+    "vendor/lib.py",
+    "tiny.py",
+];
+
+// ── Fixture ───────────────────────────────────────────────────────────────────
+
+/// `n` distinct lines starting at `from`, as content to commit
+fn lines(from: usize, n: usize) -> Option<String> {
+    Some(
+        (from..from + n)
+            .map(|i| format!("x{} = {}\n", i, i))
+            .collect(),
+    )
+}
+
+fn fixture() -> (TempDir, Repository) {
+    let dir = TempDir::new().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    commit(
+        &repo,
+        CAROL,
+        &[
+            ("src/core/engine.py", Some(python_fn("run", 30))),
+            ("src/api/handler.py", lines(0, 20)),
+            ("vendor/lib.py", lines(0, 20)),
+            ("tiny.py", lines(0, 3)),
+        ],
+        10,
+    );
+    commit(&repo, BOB, &[("src/api/routes.py", lines(0, 20))], 300);
+    commit(&repo, ALICE, &[("src/core/util.py", lines(0, 20))], 390);
+    commit(
+        &repo,
+        ALICE,
+        &[
+            ("src/api/handler.py", lines(0, 25)),
+            ("CODEOWNERS", Some("/vendor/ @platform\n".to_string())),
+        ],
+        395,
+    );
+    (dir, repo)
+}
+
+fn files(root: &Path) -> Vec<PathBuf> {
+    FILES.iter().map(|f| root.join(f)).collect()
+}
+
+/// The function in `engine.py` (complexity 31)
+fn graph(root: &Path) -> CodeGraph {
+    let mut graph = CodeGraph::new(root.to_path_buf());
+    let mut node = Node::new(
+        NodeKind::Function,
+        "run".to_string(),
+        root.join("src/core/engine.py"),
+        1,
+        NodeData::Function {
+            parameters: Vec::new(),
+            return_type: None,
+        },
+    );
+    node.set_end_line(32);
+    graph.add_node(node);
+    graph
+}
+
+fn config(active: &[&str]) -> RevetConfig {
+    let mut config = RevetConfig::default();
+    config.modules.ownership = true;
+    config.analyzers.ownership.active = active.iter().map(|a| a.to_string()).collect();
+    config
+}
+
+fn build(root: &Path, config: &RevetConfig) -> OwnershipReport {
+    OwnershipReport::build(
+        &files(root),
+        &graph(root),
+        root,
+        config,
+        &GitObjects::default(),
+    )
+    .unwrap()
+}
+
+fn paths(report: &OwnershipReport) -> Vec<&str> {
+    report.ranking.iter().map(|o| o.path.as_str()).collect()
+}
+
+// ── Orphans ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_roster_orphans_code_of_everyone_else() {
+    let (dir, _repo) = fixture();
+    let report = build(dir.path(), &config(&["alice@example.com"]));
+
+    assert_eq!(report.active, 1);
+    assert_eq!(report.blamed, 6);
+    assert_eq!(report.codeowned, 1);
+    assert_eq!(report.skipped, 0);
+    assert!(!report.shallow);
+    assert_eq!(
+        paths(&report),
+        vec!["src/core/engine.py", "src/api/routes.py"]
+    );
+
+    let engine = &report.ranking[0];
+    assert_eq!(engine.lines, 32);
+    assert_eq!(engine.departed, 100);
+    assert_eq!(engine.owners, vec!["carol@example.com"]);
+    assert_eq!(engine.idle_days, 385);
+    assert_eq!(engine.complexity, 31);
+    assert!(engine.escalated);
+
+    let routes = &report.ranking[1];
+    assert_eq!(routes.owners, vec!["bob@example.com"]);
+    assert_eq!(routes.idle_days, 95);
+    assert!(!routes.escalated);
+}
+
+#[test]
+fn test_roster_entries_match_local_part_and_domain_prefix() {
+    let (dir, _repo) = fixture();
+    for entry in ["alice", "ALICE@", "alice@example.com"] {
+        let report = build(dir.path(), &config(&[entry]));
+        assert_eq!(
+            paths(&report),
+            vec!["src/core/engine.py", "src/api/routes.py"],
+            "{entry}"
+        );
+    }
+    let report = build(dir.path(), &config(&["alice", "bob"]));
+    assert_eq!(paths(&report), vec!["src/core/engine.py"]);
+}
+
+#[test]
+fn test_roster_file_is_read_with_comments() {
+    let (dir, _repo) = fixture();
+    fs::write(
+        dir.path().join("ROSTER"),
+        "# current team\nalice@example.com\n\nbob@example.com  # on leave\n",
+    )
+    .unwrap();
+    let mut config = config(&[]);
+    config.analyzers.ownership.roster_file = Some("ROSTER".to_string());
+    let report = build(dir.path(), &config);
+    assert_eq!(report.active, 2);
+    assert_eq!(paths(&report), vec!["src/core/engine.py"]);
+
+    config.analyzers.ownership.roster_file = Some("MISSING".to_string());
+    assert!(OwnershipReport::build(
+        &files(dir.path()),
+        &graph(dir.path()),
+        dir.path(),
+        &config,
+        &GitObjects::default(),
+    )
+    .is_err());
+}
+
+#[test]
+fn test_without_roster_window_committers_are_active() {
+    let (dir, _repo) = fixture();
+    let report = build(dir.path(), &config(&[]));
+    assert_eq!(report.active, 2);
+    assert_eq!(paths(&report), vec!["src/core/engine.py"]);
+
+    // A window that ends before Bob's commit leaves only Alice
+    let mut config = config(&[]);
+    config.analyzers.ownership.window = "30d".to_string();
+    let report = build(dir.path(), &config);
+    assert_eq!(report.active, 1);
+    assert_eq!(
+        paths(&report),
+        vec!["src/core/engine.py", "src/api/routes.py"]
+    );
+}
+
+#[test]
+fn test_min_share_and_directory_rollup() {
+    let (dir, _repo) = fixture();
+    // Alice's 5 of 25 lines in handler.py are 20%
+    let mut config = config(&["alice"]);
+    config.analyzers.ownership.min_share = 21;
+    let report = build(dir.path(), &config);
+    assert_eq!(
+        paths(&report),
+        vec![
+            "src/core/engine.py",
+            "src/api/handler.py",
+            "src/api/routes.py"
+        ]
+    );
+    assert_eq!(report.ranking[1].departed, 80);
+    assert_eq!(
+        report.ranking[1].owners,
+        vec!["carol@example.com"],
+        "Carol alone wrote most lines"
+    );
+
+    // Per directory, Alice's 5 of 45 lines in src/api are too few while her
+    // util.py covers 20 of 52 lines in src/core
+    config.analyzers.ownership.rollup = "directory".to_string();
+    let report = build(dir.path(), &config);
+    assert_eq!(paths(&report), vec!["src/api"]);
+    let api = &report.ranking[0];
+    assert_eq!(api.lines, 45);
+    assert_eq!(api.departed, 89);
+    assert_eq!(api.owners, vec!["bob@example.com", "carol@example.com"]);
+    assert_eq!(api.idle_days, 0);
+}
+
+#[test]
+fn test_critical_paths_escalate() {
+    let (dir, _repo) = fixture();
+    let mut config = config(&["alice"]);
+    config.risk.critical_paths = vec!["src/api/**".to_string()];
+    config.analyzers.ownership.complexity = 100;
+    let report = build(dir.path(), &config);
+    let escalated: Vec<(&str, bool)> = report
+        .ranking
+        .iter()
+        .map(|o| (o.path.as_str(), o.escalated))
+        .collect();
+    assert_eq!(
+        escalated,
+        vec![("src/core/engine.py", false), ("src/api/routes.py", true)]
+    );
+}
+
+#[test]
+fn test_hash_authors_hides_identities() {
+    let (dir, _repo) = fixture();
+    let mut config = config(&["alice"]);
+    config.analyzers.ownership.hash_authors = true;
+    let report = build(dir.path(), &config);
+    let hashed = display("carol@example.com", true);
+    assert!(hashed.starts_with("author-") && hashed.len() == 15);
+    assert_eq!(report.ranking[0].owners, vec![hashed]);
+    assert_ne!(report.ranking[0].owners, report.ranking[1].owners);
+    for finding in report.findings(dir.path()) {
+        assert!(
+            !finding.message.contains("@example.com"),
+            "{}",
+            finding.message
+        );
+    }
+}
+
+#[test]
+fn test_max_files_bounds_blame() {
+    let (dir, _repo) = fixture();
+    let mut config = config(&["alice"]);
+    config.analyzers.ownership.max_files = 2;
+    let report = build(dir.path(), &config);
+    // Sorted paths: src/api/handler.py and src/api/routes.py
+    assert_eq!(report.blamed, 2);
+    assert_eq!(report.skipped, 4);
+    assert_eq!(paths(&report), vec!["src/api/routes.py"]);
+}
+
+#[test]
+fn test_authorship_is_cached_per_head() {
+    let (dir, repo) = fixture();
+    let config = config(&["alice"]);
+    build(dir.path(), &config);
+    let cache = dir.path().join(".revet-cache/authorship.json");
+    let cached: serde_json::Value = serde_json::from_slice(&fs::read(&cache).unwrap()).unwrap();
+    assert_eq!(cached["files"].as_object().unwrap().len(), 6);
+
+    // Alice takes over routes.py: the new HEAD invalidates the cache
+    commit(&repo, ALICE, &[("src/api/routes.py", lines(100, 20))], 396);
+    let report = build(dir.path(), &config);
+    assert_eq!(paths(&report), vec!["src/core/engine.py"]);
+}
+
+// ── Findings ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_dispatcher_reports_orphans() {
+    let (dir, _repo) = fixture();
+    let root = dir.path();
+    let config = config(&["alice"]);
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config);
+    let (findings, report) = dispatcher
+        .run_ownership(&files(root), &graph(root), root, &config)
+        .expect("module on")
+        .unwrap();
+    assert_eq!(report.ranking.len(), 2);
+    assert_eq!(findings.len(), 2);
+
+    assert_eq!(findings[0].id, "OWN-001");
+    assert_eq!(findings[0].severity, Severity::Warning);
+    assert_eq!(findings[0].file, root.join("src/core/engine.py"));
+    assert_eq!(findings[0].line, 1);
+    assert!(
+        findings[0].message.contains("100%"),
+        "{}",
+        findings[0].message
+    );
+    assert!(
+        findings[0].message.contains("12 months"),
+        "{}",
+        findings[0].message
+    );
+    assert!(findings[0].suggestion.is_some());
+
+    assert_eq!(findings[1].severity, Severity::Info);
+    assert!(findings[1].message.contains("bob@example.com"));
+    assert!(
+        findings[1].message.contains("3 months"),
+        "{}",
+        findings[1].message
+    );
+}
+
+#[test]
+fn test_dispatcher_skips_when_disabled_and_fails_outside_git() {
+    let dir = TempDir::new().unwrap();
+    let dispatcher = AnalyzerDispatcher::new();
+    let graph = graph(dir.path());
+    assert!(dispatcher
+        .run_ownership(&[], &graph, dir.path(), &RevetConfig::default())
+        .is_none());
+    assert!(dispatcher
+        .run_ownership(&[], &graph, dir.path(), &config(&[]))
+        .unwrap()
+        .is_err());
+}
+
+// ── CODEOWNERS ────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_codeowners_patterns() {
+    let owners = CodeOwners::parse(
+        "# comment\n\
+         *.md @docs\n\
+         /build/ @infra\n\
+         src/*/gen.rs @codegen\n\
+         lib/ @core\n\
+         lib/unowned/\n\
+         /Makefile @infra # trailing comment\n",
+    );
+    assert!(owners.owns("README.md"));
+    assert!(owners.owns("docs/guide/intro.md"));
+    assert!(owners.owns("build/out.js"));
+    assert!(!owners.owns("src/build/out.js"));
+    assert!(owners.owns("src/a/gen.rs"));
+    assert!(!owners.owns("src/a/b/gen.rs"));
+    assert!(owners.owns("lib/x.py"));
+    assert!(owners.owns("pkg/lib/x.py"));
+    assert!(!owners.owns("lib/unowned/x.py"));
+    assert!(owners.owns("Makefile"));
+    assert!(!owners.owns("src/Makefile"));
+    assert!(!owners.owns("main.py"));
+}
+
+#[test]
+fn test_codeowners_location() {
+    let dir = TempDir::new().unwrap();
+    assert!(!CodeOwners::load(dir.path()).owns("a.py"));
+    fs::create_dir_all(dir.path().join(".github")).unwrap();
+    fs::write(dir.path().join(".github/CODEOWNERS"), "* @team\n").unwrap();
+    fs::write(dir.path().join("CODEOWNERS"), "").unwrap();
+    assert!(CodeOwners::load(dir.path()).owns("a.py"));
+}
+
+// ── Config ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_config_validates_ownership_settings() {
+    let config: RevetConfig = toml::from_str(
+        "[modules]\nownership = true\n\n[analyzers.ownership]\nactive = [\"alice\"]\nrollup = \"directory\"\n",
+    )
+    .unwrap();
+    assert!(config.modules.ownership);
+    assert_eq!(config.analyzers.ownership.window, "180d");
+    assert_eq!(config.analyzers.ownership.min_share, 10);
+    assert!(config.validate().0.is_empty());
+
+    let config: RevetConfig = toml::from_str(
+        "[analyzers.ownership]\nwindow = \"soon\"\nmin_share = 0\nrollup = \"team\"\nactive = [\" \"]\n",
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    assert_eq!(errors.len(), 4, "{errors:?}");
+}
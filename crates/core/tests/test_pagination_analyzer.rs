@@ -0,0 +1,304 @@
+//! Integration tests for PaginationAnalyzer
+
+mod common;
+
+use revet_core::analyzer::pagination::handlers::unbounded_select;
+use revet_core::analyzer::pagination::PaginationAnalyzer;
+use revet_core::analyzer::{Analyzer, AnalyzerDispatcher};
+use revet_core::config::{PaginationConfig, RevetConfig};
+use revet_core::finding::{Confidence, Finding, Severity};
+use std::path::Path;
+use tempfile::TempDir;
+
+const FIXTURE: &str = "pagination";
+const EXTENSIONS: &[&str] = &[".py", ".ts", ".js"];
+
+/// (repo-relative file, line) for each PAGE finding, sorted
+fn summary(findings: &[Finding], root: &Path) -> Vec<(String, usize)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("PAGE"))
+        .map(|f| {
+            let file = f.file.strip_prefix(root).unwrap_or(&f.file);
+            (file.to_string_lossy().replace('\\', "/"), f.line)
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+fn row(file: &str, line: usize) -> (String, usize) {
+    (file.to_string(), line)
+}
+
+fn analyze_with(config: &PaginationConfig, file: &str, content: &str) -> Vec<Finding> {
+    let tmp = TempDir::new().unwrap();
+    PaginationAnalyzer::with_config(config).analyze_source(
+        &tmp.path().join(file),
+        content,
+        tmp.path(),
+    )
+}
+
+fn analyze(file: &str, content: &str) -> Vec<Finding> {
+    analyze_with(&PaginationConfig::default(), file, content)
+}
+
+fn lines(findings: &[Finding]) -> Vec<usize> {
+    findings.iter().map(|f| f.line).collect()
+}
+
+#[test]
+fn test_fixture_findings() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let expected = vec![
+        row("api/users.py", 13),
+        row("api/users.py", 19),
+        row("api/users.py", 25),
+        row("shop/views.py", 12),
+        row("web/reports.js", 6),
+        row("web/users.ts", 8),
+        row("web/users.ts", 24),
+    ];
+    assert_eq!(summary(&findings, &root), expected);
+}
+
+#[test]
+fn test_paginated_versions_pass() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    assert!(findings
+        .iter()
+        .all(|f| f.file != root.join("api/users_paged.py")));
+}
+
+#[test]
+fn test_severity_confidence_and_message() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    assert!(findings
+        .iter()
+        .all(|f| f.severity == Severity::Warning && f.confidence == Confidence::Medium));
+    let first = findings
+        .iter()
+        .find(|f| f.file == root.join("api/users.py"))
+        .unwrap();
+    assert_eq!(
+        first.message,
+        "`list_users` returns every result of `db.query().all()` without pagination"
+    );
+    let express = findings
+        .iter()
+        .find(|f| f.file == root.join("web/users.ts"))
+        .unwrap();
+    assert!(express.message.starts_with("`GET /users` returns"));
+}
+
+#[test]
+fn test_suggestion_names_the_pagination_idiom() {
+    let cases = [
+        (
+            "a.py",
+            "@app.get('/u')\ndef f():\n    return db.query(User).all()\n",
+            "`.limit()`",
+        ),
+        (
+            "views.py",
+            "class V(ViewSet):\n    def list(self, request):\n        return Response(S(Order.objects.all(), many=True).data)\n",
+            "PageNumberPagination",
+        ),
+        (
+            "a.ts",
+            "app.get('/u', async (req, res) => res.json(await prisma.user.findMany()));\n",
+            "`take`",
+        ),
+        (
+            "a.js",
+            "fastify.get('/u', async (request, reply) => {\n  return db.query('SELECT * FROM users');\n});\n",
+            "Fastify pagination plugin",
+        ),
+        (
+            "b.js",
+            "app.get('/u', async (req, res) => {\n  res.json(await User.find({}));\n});\n",
+            "`.limit()` and `.skip()`",
+        ),
+        (
+            "c.js",
+            "app.get('/u', async (req, res) => {\n  res.send(await User.findAll({ where: {} }));\n});\n",
+            "`findAndCountAll`",
+        ),
+        (
+            "b.py",
+            "@bp.route('/u')\ndef f():\n    cur.execute(\"SELECT * FROM users\")\n    return jsonify(cur.fetchall())\n",
+            "`LIMIT`",
+        ),
+    ];
+    for (file, source, expected) in cases {
+        let findings = analyze(file, source);
+        assert_eq!(findings.len(), 1, "{}", file);
+        let suggestion = findings[0].suggestion.as_deref().unwrap();
+        assert!(suggestion.contains(expected), "{}: {}", file, suggestion);
+    }
+}
+
+#[test]
+fn test_result_followed_through_assignments() {
+    let findings = analyze(
+        "a.py",
+        "\
+@app.get('/a')
+def a():
+    users = User.query.all()
+    out = [dump(u) for u in users]
+    return {'items': out}
+
+@app.get('/b')
+def b():
+    users = User.query.all()
+    users = users[:10]
+    return users
+
+@app.get('/c')
+def c():
+    users = User.query.all()
+    return {'count': len(users), 'first': users[0]}
+
+def helper():
+    return User.query.all()
+",
+    );
+    assert_eq!(lines(&findings), vec![5]);
+}
+
+#[test]
+fn test_bounded_queries_pass() {
+    for source in [
+        "@app.get('/u')\ndef f():\n    return db.query(User).limit(20).all()\n",
+        "@app.get('/u')\ndef f():\n    return db.query(User).filter(User.id == 1).first()\n",
+        "@app.get('/u')\ndef f():\n    return User.objects.all()[:20]\n",
+        "@app.get('/u')\ndef f(page: int = 1):\n    return User.query.all()\n",
+        "@app.get('/u')\ndef f(page_size: int = 20):\n    return User.query.all()\n",
+        "@app.get('/u')\ndef f():\n    return db.execute('SELECT * FROM users LIMIT 10').fetchall()\n",
+        "@app.get('/u')\ndef f():\n    return db.execute('SELECT count(*) FROM users').fetchall()\n",
+    ] {
+        assert!(analyze("a.py", source).is_empty(), "{}", source);
+    }
+    for source in [
+        "app.get('/u', async (req, res) => res.json(await prisma.user.findMany({ take: 10 })));\n",
+        "app.get('/u', async (req, res) => res.json((await prisma.user.findMany()).slice(0, 10)));\n",
+        "app.get('/u', async (req, res) => res.json({ n: (await prisma.user.findMany()).length }));\n",
+        "app.get('/u', async (req, res) => res.json(await User.find({}).limit(10)));\n",
+        "app.get('/u', async (req, res) => {\n  const { perPage } = req.query;\n  res.json(await prisma.user.findMany());\n});\n",
+        "app.get('/u', async (req, res) => res.json(users.find((u) => u.id === req.params.id)));\n",
+    ] {
+        assert!(analyze("a.ts", source).is_empty(), "{}", source);
+    }
+}
+
+#[test]
+fn test_only_handlers_are_checked() {
+    let source = "\
+def all_users():
+    return User.query.all()
+
+@cache
+def cached():
+    return User.query.all()
+
+class Service:
+    def list(self):
+        return User.query.all()
+";
+    assert!(analyze("a.py", source).is_empty());
+    let source = "\
+async function allUsers() {
+  return prisma.user.findMany();
+}
+cache.get('key', () => prisma.user.findMany());
+";
+    assert!(analyze("a.ts", source).is_empty());
+}
+
+#[test]
+fn test_unbounded_ok_and_small_tables() {
+    let source = "\
+@app.get('/countries')
+def countries():
+    return Country.query.all()
+
+# revet: unbounded-ok
+@app.get('/roles')
+def roles():
+    return Role.query.all()
+
+@app.get('/users')
+def users():
+    return User.query.all()
+";
+    assert_eq!(lines(&analyze("a.py", source)), vec![3, 12]);
+    let config = PaginationConfig {
+        small_tables: vec!["country".to_string()],
+    };
+    assert_eq!(lines(&analyze_with(&config, "a.py", source)), vec![12]);
+
+    let source = "\
+app.get('/countries', async (req, res) => {
+  res.json(await prisma.country.findMany());
+});
+app.get('/rows', async (req, res) => {
+  res.json(await db.query('SELECT * FROM public.country'));
+});
+";
+    assert!(analyze_with(&config, "a.ts", source).is_empty());
+}
+
+#[test]
+fn test_unbounded_select() {
+    assert_eq!(
+        unbounded_select("\"SELECT id, name FROM users WHERE active\""),
+        Some("users".to_string())
+    );
+    assert_eq!(
+        unbounded_select("f\"select * from `orders` order by id\""),
+        Some("orders".to_string())
+    );
+    for sql in [
+        "'SELECT * FROM users LIMIT 10'",
+        "'SELECT TOP 10 * FROM users'",
+        "'SELECT * FROM users OFFSET 10 ROWS FETCH NEXT 10 ROWS ONLY'",
+        "'SELECT COUNT(*) FROM users'",
+        "'UPDATE users SET active = 1'",
+    ] {
+        assert_eq!(unbounded_select(sql), None, "{}", sql);
+    }
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("api.py");
+    std::fs::write(
+        &file,
+        "@app.get('/u')\ndef f():\n    return User.query.all()\n",
+    )
+    .unwrap();
+    let config = RevetConfig::default();
+    let findings =
+        AnalyzerDispatcher::new_with_config(&config).run_all(&[file], tmp.path(), &config);
+    assert!(findings.iter().all(|f| !f.id.starts_with("PAGE")));
+}
+
+#[test]
+fn test_config_validation() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[analyzers.pagination]
+small_tables = ["countries", " "]
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    assert!(
+        errors.iter().any(|e| e.contains("small_tables")),
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,261 @@
+//! Parser conformance: every registered parser against the generated
+//! fixtures' manifests
+//!
+//! The baseline lists, per parser, the checks its fixtures fail today —
+//! the known inconsistencies between parsers. A parser fixed for a check
+//! fails `test_conformance_baseline` until its entry is removed, so the
+//! baseline only ever shrinks.
+//!
+//! Parsers also report whether they record documentation comments; the
+//! list of those that do grows as parsers are taught to.
+
+use revet_core::parser::conformance::{self, Check};
+use revet_core::{CodeGraph, NodeKind, ParserDispatcher};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use Check::*;
+
+/// Seeds every parser is checked with
+const SEEDS: std::ops::Range<u64> = 0..8;
+
+/// Known inconsistencies, per parser
+const BASELINE: &[(&str, &[Check])] = &[
+    // Nested struct definitions are not extracted; `...` is a parameter
+    ("c/cpp", &[Missing, Parameters, Count]),
+    // Attributed types start at the attribute; `params` arrays are dropped;
+    // members hang off no Contains edge
+    ("csharp", &[Line, Parameters, Containment, Invariant]),
+    // Annotated types start at the annotation; a varargs parameter has no
+    // name; members hang off no Contains edge
+    ("java", &[Line, Parameters, Containment, Invariant]),
+    // `import a.b.*` keeps the `*` in the module; annotated types start at
+    // the annotation; members hang off no Contains edge
+    ("kotlin", &[Missing, Line, Containment, Invariant]),
+    // Names are qualified by the file's namespace; attributed classes start
+    // at the attribute; `...$rest` keeps its dots; methods hang off no
+    // Contains edge
+    (
+        "php",
+        &[QualifiedName, Line, Parameters, Containment, Invariant],
+    ),
+    // `import a.b as c` keeps the alias in the module; nested classes are
+    // not extracted; methods are neither qualified by nor contained in their
+    // class; `*args` and `**kwargs` are dropped
+    (
+        "python",
+        &[
+            Missing,
+            QualifiedName,
+            Parameters,
+            Containment,
+            Count,
+            Invariant,
+        ],
+    ),
+    // Parameters keep their sigils (`*rest`, `key:`, `&block`); nested
+    // types hang off no Contains edge
+    ("ruby", &[Parameters, Containment, Invariant]),
+    // Items inside inline `mod` blocks are not extracted
+    ("rust", &[Missing, Count]),
+    // Attributed types start at the attribute; variadic parameters keep
+    // their `...`; members hang off no Contains edge
+    ("swift", &[Line, Parameters, Containment, Invariant]),
+    // `require` calls and namespaces (with what they hold) are not
+    // extracted; `...rest` keeps its dots; function locals hang off no
+    // Contains edge
+    ("typescript", &[Missing, Parameters, Count, Invariant]),
+];
+
+/// Parsers that record documentation comments, which the `DOC` analyzer
+/// covers
+const DOC_COMMENTS: &[&str] = &["csharp", "go", "java", "python", "rust", "typescript"];
+
+/// Checks each parser's fixtures fail, with an example mismatch for each
+fn failures() -> BTreeMap<String, BTreeMap<Check, String>> {
+    let dispatcher = ParserDispatcher::new();
+    let mut failures: BTreeMap<String, BTreeMap<Check, String>> = BTreeMap::new();
+    for parser in dispatcher.parsers() {
+        let language = parser.language_name();
+        let failed = failures.entry(language.to_string()).or_default();
+        for seed in SEEDS {
+            let fixture = conformance::generate(language, seed)
+                .unwrap_or_else(|| panic!("no conformance fixture for {}", language));
+            let mut graph = CodeGraph::new(PathBuf::from("/conformance"));
+            parser
+                .parse_source(
+                    &fixture.source,
+                    &PathBuf::from(&fixture.file_name),
+                    &mut graph,
+                )
+                .unwrap_or_else(|e| panic!("{} seed {}: {}", language, seed, e));
+            for mismatch in conformance::check(&fixture.manifest, &graph) {
+                failed
+                    .entry(mismatch.check)
+                    .or_insert_with(|| format!("seed {}: {}", seed, mismatch));
+            }
+        }
+    }
+    failures
+}
+
+#[test]
+fn test_conformance_baseline() {
+    let baseline: BTreeMap<&str, BTreeSet<Check>> = BASELINE
+        .iter()
+        .map(|(language, checks)| (*language, checks.iter().copied().collect()))
+        .collect();
+
+    let mut problems = Vec::new();
+    for (language, failed) in failures() {
+        let known = baseline.get(language.as_str()).cloned().unwrap_or_default();
+        for (check, example) in &failed {
+            if !known.contains(check) {
+                problems.push(format!("{}: new {} failure — {}", language, check, example));
+            }
+        }
+        for check in known.iter().filter(|c| !failed.contains_key(c)) {
+            problems.push(format!(
+                "{}: {} passes now — remove it from the baseline",
+                language, check
+            ));
+        }
+    }
+    assert!(problems.is_empty(), "{}", problems.join("\n"));
+}
+
+#[test]
+fn test_every_parser_has_fixtures() {
+    let dispatcher = ParserDispatcher::new();
+    let languages: Vec<&str> = conformance::languages().collect();
+    for parser in dispatcher.parsers() {
+        assert!(
+            languages.contains(&parser.language_name()),
+            "no conformance fixture for {}",
+            parser.language_name()
+        );
+    }
+}
+
+#[test]
+fn test_baseline_names_registered_parsers() {
+    let dispatcher = ParserDispatcher::new();
+    let registered: Vec<&str> = dispatcher.parsers().map(|p| p.language_name()).collect();
+    for (language, _) in BASELINE {
+        assert!(registered.contains(language), "unknown parser {}", language);
+    }
+}
+
+#[test]
+fn test_doc_comment_capability() {
+    let dispatcher = ParserDispatcher::new();
+    for parser in dispatcher.parsers() {
+        let language = parser.language_name();
+        let capable = DOC_COMMENTS.contains(&language);
+        assert_eq!(
+            parser.doc_style().is_some(),
+            capable,
+            "{}: update DOC_COMMENTS",
+            language
+        );
+
+        // A capable parser records every declaration, documented or not
+        let fixture = conformance::generate(language, 0).unwrap();
+        let mut graph = CodeGraph::new(PathBuf::from("/conformance"));
+        parser
+            .parse_source(
+                &fixture.source,
+                &PathBuf::from(&fixture.file_name),
+                &mut graph,
+            )
+            .unwrap();
+        let declarations: Vec<_> = graph
+            .nodes()
+            .filter(|(_, n)| matches!(n.kind(), NodeKind::Function | NodeKind::Class))
+            .collect();
+        assert!(!declarations.is_empty(), "{}", language);
+        for (_, node) in declarations {
+            assert_eq!(
+                node.doc().is_recorded(),
+                capable,
+                "{}: {}",
+                language,
+                node.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_generate_is_deterministic() {
+    for language in conformance::languages() {
+        let first = conformance::generate(language, 42).unwrap();
+        let second = conformance::generate(language, 42).unwrap();
+        assert_eq!(first.source, second.source);
+        assert_eq!(first.manifest, second.manifest);
+        assert_ne!(
+            first.source,
+            conformance::generate(language, 43).unwrap().source,
+            "{}: seeds should vary the fixture",
+            language
+        );
+    }
+}
+
+#[test]
+fn test_generate_by_extension() {
+    let by_name = conformance::generate("swift", 7).unwrap();
+    assert_eq!(by_name.file_name, "conformance_7.swift");
+    let by_extension = conformance::generate("py", 7).unwrap();
+    assert_eq!(by_extension.manifest.language, "python");
+    assert_eq!(
+        conformance::generate("c", 7).unwrap().manifest.language,
+        "c/cpp"
+    );
+    assert!(conformance::generate("cobol", 7).is_none());
+}
+
+#[test]
+fn test_manifest_lines_point_at_names() {
+    for language in conformance::languages() {
+        let fixture = conformance::generate(language, 3).unwrap();
+        let lines: Vec<&str> = fixture.source.lines().collect();
+        for entity in &fixture.manifest.entities {
+            let last = entity.name.rsplit('.').next().unwrap();
+            let line = lines[entity.line - 1];
+            // Imports name modules, which the statement spells its own way
+            if entity.kind != revet_core::graph::NodeKind::Import {
+                assert!(
+                    line.contains(last) || line.contains(&last.to_lowercase()),
+                    "{}: line {} `{}` does not declare {}",
+                    language,
+                    entity.line,
+                    line,
+                    entity.name
+                );
+            }
+            assert!(entity.end_line >= entity.line);
+        }
+        let total: usize = fixture.manifest.counts.values().sum();
+        assert_eq!(total, fixture.manifest.entities.len());
+    }
+}
+
+#[test]
+fn test_manifest_round_trips_as_json() {
+    let fixture = conformance::generate("kotlin", 11).unwrap();
+    let json = serde_json::to_string(&fixture.manifest).unwrap();
+    let manifest: conformance::Manifest = serde_json::from_str(&json).unwrap();
+    assert_eq!(manifest, fixture.manifest);
+}
+
+#[test]
+fn test_check_reports_missing_entities() {
+    let fixture = conformance::generate("go", 0).unwrap();
+    let graph = CodeGraph::new(PathBuf::from("/conformance"));
+    let mismatches = conformance::check(&fixture.manifest, &graph);
+    let missing = mismatches
+        .iter()
+        .filter(|m| m.check == Check::Missing)
+        .count();
+    assert_eq!(missing, fixture.manifest.entities.len());
+}
@@ -0,0 +1,135 @@
+//! Time-boxed structured-mutation fuzzing of every registered parser
+//!
+//! Mutates the seed corpus (`parser::fuzz::seeds`) and parses each input on
+//! a thread of its own, failing on a panic, on a parse that outlives
+//! `TIMEOUT`, or on a graph that fails `CodeGraph::validate`.
+//!
+//! `REVET_FUZZ_SECONDS` sets the budget (default 3) and `REVET_FUZZ_SEED`
+//! the first seed (default 0), so a plain `cargo test` is deterministic and
+//! CI can run longer with a fresh seed. A failing input is written to the
+//! temp directory and named in the failure.
+
+use revet_core::parser::conformance::Rng;
+use revet_core::parser::fuzz;
+use revet_core::ParserDispatcher;
+use std::path::Path;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Longest a single parse may take
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Parsers whose conformance fixtures pass the `invariant` check: for
+/// these, nodes outside their file's `Contains` tree are failures too
+const CONTAINED: &[&str] = &["c/cpp", "go", "rust"];
+
+fn env_u64(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Parse `input` with the `index`th parser on its own thread
+fn run(dispatcher: &Arc<ParserDispatcher>, index: usize, input: String) -> Result<(), String> {
+    let (sender, receiver) = mpsc::channel();
+    let dispatcher = Arc::clone(dispatcher);
+    let worker = thread::Builder::new()
+        .stack_size(16 * 1024 * 1024)
+        .spawn(move || {
+            let parser = dispatcher.parsers().nth(index).unwrap();
+            let containment = CONTAINED.contains(&parser.language_name());
+            let _ = sender.send(fuzz::check(parser, &input, containment));
+        })
+        .unwrap();
+    match receiver.recv_timeout(TIMEOUT) {
+        Ok(result) => {
+            let _ = worker.join();
+            result
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            Err(format!("no result within {}s", TIMEOUT.as_secs()))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err("panicked".to_string()),
+    }
+}
+
+#[test]
+fn test_fuzz_parsers() {
+    let seconds = env_u64("REVET_FUZZ_SECONDS", 3);
+    let seed = env_u64("REVET_FUZZ_SEED", 0);
+    let repo_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let dispatcher = Arc::new(ParserDispatcher::new());
+    let corpora: Vec<Vec<String>> = dispatcher
+        .parsers()
+        .map(|parser| fuzz::seeds(&repo_root, parser))
+        .collect();
+
+    let mut rng = Rng::new(seed);
+    let deadline = Instant::now() + Duration::from_secs(seconds);
+    let mut inputs = 0;
+    // Every parser at least once, however short the budget
+    while inputs < corpora.len() || Instant::now() < deadline {
+        let index = inputs % corpora.len();
+        let seed_input: &String = rng.pick(&corpora[index]);
+        let input = fuzz::mutate(seed_input, &mut rng);
+        if let Err(failure) = run(&dispatcher, index, input.clone()) {
+            let parser = dispatcher.parsers().nth(index).unwrap();
+            let path = std::env::temp_dir().join(format!(
+                "revet-fuzz-{}-{}",
+                seed,
+                fuzz::file_name(parser).display()
+            ));
+            std::fs::write(&path, &input).unwrap();
+            panic!(
+                "{} (REVET_FUZZ_SEED={}, input #{}, saved to {}):\n{}",
+                parser.language_name(),
+                seed,
+                inputs,
+                path.display(),
+                failure
+            );
+        }
+        inputs += 1;
+    }
+    eprintln!("fuzzed {} inputs in {}s", inputs, seconds);
+}
+
+#[test]
+fn test_seed_corpora() {
+    let repo_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let dispatcher = ParserDispatcher::new();
+    for parser in dispatcher.parsers() {
+        let seeds = fuzz::seeds(&repo_root, parser);
+        // Test file literals and fixtures on top of the conformance fixtures
+        assert!(
+            seeds.len() > 4,
+            "{}: {} seeds",
+            parser.language_name(),
+            seeds.len()
+        );
+        // Every seed parses cleanly before mutation
+        for seed in &seeds {
+            let containment = CONTAINED.contains(&parser.language_name());
+            if let Err(failure) = fuzz::check(parser, seed, containment) {
+                panic!(
+                    "{} seed fails:\n{}\n{}",
+                    parser.language_name(),
+                    failure,
+                    seed
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_mutate_is_deterministic() {
+    let source = "def f(a, b):\n    return a + b\n\nclass C:\n    pass\n";
+    let first = fuzz::mutate(source, &mut Rng::new(9));
+    let second = fuzz::mutate(source, &mut Rng::new(9));
+    assert_eq!(first, second);
+    let mutated = (0..32).filter(|&s| fuzz::mutate(source, &mut Rng::new(s)) != source);
+    assert!(mutated.count() > 24);
+}
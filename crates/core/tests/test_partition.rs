@@ -0,0 +1,153 @@
+//! Tests for directory partitions and partitioned analyzer runs
+
+use revet_core::analyzer::partition::{partition, partition_by, Partition};
+use revet_core::{AnalyzerDispatcher, Finding, RevetConfig};
+use std::path::{Path, PathBuf};
+
+fn files(root: &Path, rel: &[&str]) -> Vec<PathBuf> {
+    rel.iter().map(|r| root.join(r)).collect()
+}
+
+fn dirs(partitions: &[Partition]) -> Vec<Vec<&str>> {
+    partitions
+        .iter()
+        .map(|p| p.dirs.iter().map(String::as_str).collect())
+        .collect()
+}
+
+#[test]
+fn test_small_directories_share_a_partition() {
+    let root = Path::new("/repo");
+    let files = files(
+        root,
+        &["README.py", "api/a.py", "api/b.py", "lib/c.py", "web/d.ts"],
+    );
+    let partitions = partition_by(&files, root, 3);
+    assert_eq!(
+        dirs(&partitions),
+        vec![vec![".", "api"], vec!["lib", "web"]]
+    );
+    assert_eq!(partitions[0].files, 0..3);
+    assert_eq!(partitions[1].files, 3..5);
+    assert_eq!(partitions[0].label(), "., api/");
+}
+
+#[test]
+fn test_large_directory_is_split() {
+    let root = Path::new("/repo");
+    let files = files(
+        root,
+        &[
+            "src/a.rs", "src/b.rs", "src/c.rs", "src/d.rs", "src/e.rs", "z/f.rs",
+        ],
+    );
+    let partitions = partition_by(&files, root, 2);
+    let ranges: Vec<_> = partitions.iter().map(|p| p.files.clone()).collect();
+    assert_eq!(ranges, vec![0..2, 2..4, 4..6]);
+    assert_eq!(
+        dirs(&partitions),
+        vec![vec!["src"], vec!["src"], vec!["src", "z"]]
+    );
+}
+
+#[test]
+fn test_partitions_cover_every_file_in_order() {
+    let root = Path::new("/repo");
+    let rel: Vec<String> = (0..400)
+        .map(|i| format!("d{:02}/f{:03}.py", i / 13, i))
+        .collect();
+    let files: Vec<PathBuf> = rel.iter().map(|r| root.join(r)).collect();
+    let partitions = partition(&files, root);
+    assert!(partitions.len() > 1);
+    assert!(partitions.len() <= 16);
+    let mut next = 0;
+    for p in &partitions {
+        assert_eq!(p.files.start, next);
+        assert!(!p.files.is_empty());
+        next = p.files.end;
+    }
+    assert_eq!(next, files.len());
+}
+
+#[test]
+fn test_small_repository_is_one_partition() {
+    let root = Path::new("/repo");
+    let files = files(root, &["a/x.py", "b/y.py", "c/z.py"]);
+    assert_eq!(partition(&files, root).len(), 1);
+    assert!(partition(&[], root).is_empty());
+}
+
+fn key(f: &Finding) -> (String, PathBuf, usize, String) {
+    (f.id.clone(), f.file.clone(), f.line, f.message.clone())
+}
+
+#[test]
+fn test_partitioned_run_matches_single_run() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures/debug_artifacts")
+        .canonicalize()
+        .unwrap();
+    let files = revet_core::discover_files(
+        &root,
+        &[".py", ".ts", ".js", ".rb", ".php", ".rs", ".go"],
+        &[],
+    )
+    .unwrap();
+    let mut config = RevetConfig::default();
+    config.modules.debug_artifacts = true;
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config);
+
+    let (single, single_timings) = dispatcher.run_all_parallel_timed(&files, &root, &config);
+    assert!(single.iter().any(|f| f.id == "DBG-002"));
+
+    let partitions = partition_by(&files, &root, 2);
+    assert!(partitions.len() > 2);
+    let mut streamed = Vec::new();
+    let mut seen = 0;
+    let (partitioned, timings) = dispatcher.run_all_partitioned_timed(
+        &files,
+        &partitions,
+        &root,
+        &config,
+        |partition, findings| {
+            assert_eq!(*partition, partitions[seen]);
+            seen += 1;
+            let in_partition = &files[partition.files.clone()];
+            assert!(findings.iter().all(|f| in_partition.contains(&f.file)));
+            streamed.extend(findings);
+        },
+    );
+    assert_eq!(seen, partitions.len());
+
+    assert_eq!(
+        single.iter().map(key).collect::<Vec<_>>(),
+        partitioned.iter().map(key).collect::<Vec<_>>()
+    );
+    let names = |t: &[revet_core::AnalyzerTiming]| {
+        t.iter()
+            .map(|t| (t.name.clone(), t.findings))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&single_timings), names(&timings));
+
+    // Streamed findings already carry their final IDs
+    let dbg: Vec<_> = streamed
+        .iter()
+        .filter(|f| f.id.starts_with("DBG-"))
+        .collect();
+    assert!(!dbg.is_empty());
+    for f in &streamed {
+        assert!(partitioned.iter().any(|p| key(p) == key(f)), "{}", f.id);
+    }
+}
+
+#[test]
+fn test_whole_repo_analyzers_are_not_per_file() {
+    let config = RevetConfig::default();
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config);
+    let names = dispatcher.whole_repo_analyzers(&config, false);
+    assert!(names.contains(&"Secret Exposure"));
+    assert!(!names.contains(&"SQL Injection"));
+    let with_graph = dispatcher.whole_repo_analyzers(&config, true);
+    assert!(with_graph.len() > names.len());
+}
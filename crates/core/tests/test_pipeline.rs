@@ -0,0 +1,69 @@
+//! Tests for `analyze_root` — in-process analysis of a whole tree
+
+use revet_core::{analyze_root, RevetConfig};
+use tempfile::TempDir;
+
+fn write(dir: &TempDir, name: &str, content: &str) {
+    std::fs::write(dir.path().join(name), content).unwrap();
+}
+
+#[test]
+fn test_analyze_root_reports_findings_under_canonical_root() {
+    let dir = TempDir::new().unwrap();
+    write(
+        &dir,
+        "app.py",
+        "def f(cursor, x):\n    cursor.execute(f\"SELECT * FROM t WHERE id = {x}\")\n",
+    );
+
+    let analysis = analyze_root(dir.path(), &RevetConfig::default()).unwrap();
+
+    assert_eq!(analysis.root, dir.path().canonicalize().unwrap());
+    assert_eq!(analysis.files.len(), 1);
+    let sql: Vec<_> = analysis
+        .findings
+        .iter()
+        .filter(|f| f.id.starts_with("SQL-"))
+        .collect();
+    assert_eq!(sql.len(), 1, "{:?}", analysis.findings);
+    assert_eq!(sql[0].line, 2);
+    assert!(sql[0].file.starts_with(&analysis.root));
+}
+
+#[test]
+fn test_analyze_root_applies_inline_suppressions() {
+    let dir = TempDir::new().unwrap();
+    write(
+        &dir,
+        "app.py",
+        "password = \"hunter2-but-longer\"  # revet-ignore SEC\n",
+    );
+
+    let analysis = analyze_root(dir.path(), &RevetConfig::default()).unwrap();
+
+    assert!(
+        analysis.findings.iter().all(|f| !f.id.starts_with("SEC-")),
+        "{:?}",
+        analysis.findings
+    );
+    assert_eq!(analysis.suppressed.len(), 1);
+}
+
+#[test]
+fn test_analyze_root_ignores_config_on_disk() {
+    let dir = TempDir::new().unwrap();
+    write(&dir, ".revet.toml", "[modules]\nsecurity = false\n");
+    write(&dir, "app.py", "password = \"hunter2-but-longer\"\n");
+
+    let analysis = analyze_root(dir.path(), &RevetConfig::default()).unwrap();
+
+    assert!(analysis.findings.iter().any(|f| f.id.starts_with("SEC-")));
+}
+
+#[test]
+fn test_analyze_root_empty_tree() {
+    let dir = TempDir::new().unwrap();
+    let analysis = analyze_root(dir.path(), &RevetConfig::default()).unwrap();
+    assert!(analysis.files.is_empty());
+    assert!(analysis.findings.is_empty());
+}
@@ -0,0 +1,384 @@
+//! Tests for `[[policy]]`: the filter grammar, evaluation and compile errors
+
+use revet_core::policy::{self, Action, Expr, Status, Subject};
+use revet_core::{
+    CodeKind, Confidence, Finding, FixKind, PolicyConfig, RevetConfig, Severity, SuppressedFinding,
+};
+use std::path::{Path, PathBuf};
+
+const ROOT: &str = "/repo";
+
+fn finding(id: &str, severity: Severity, file: &str) -> Finding {
+    Finding {
+        id: id.to_string(),
+        severity,
+        message: format!("{} message", id),
+        file: PathBuf::from(ROOT).join(file),
+        line: 1,
+        ..Default::default()
+    }
+}
+
+fn matches(filter: &str, finding: &Finding) -> bool {
+    matches_with(filter, finding, Status::New)
+}
+
+fn matches_with(filter: &str, finding: &Finding, status: Status) -> bool {
+    let expr = Expr::parse(filter).unwrap_or_else(|e| panic!("{}: {}", filter, e));
+    expr.matches(&Subject::new(finding, Path::new(ROOT), status))
+}
+
+fn parse_error(filter: &str) -> String {
+    Expr::parse(filter)
+        .err()
+        .unwrap_or_else(|| panic!("{:?} should not parse", filter))
+        .to_string()
+}
+
+fn policy(toml: &str) -> PolicyConfig {
+    let config: RevetConfig = toml::from_str(&format!("[[policy]]\n{}", toml)).unwrap();
+    config.policies.into_iter().next().unwrap()
+}
+
+// ── Grammar ──────────────────────────────────────────────────────
+
+#[test]
+fn test_field_comparisons() {
+    let f = finding("SEC-003", Severity::Error, "payments/api/charge.py");
+    assert!(matches("id == SEC-003", &f));
+    assert!(matches("prefix == SEC", &f));
+    assert!(matches("prefix != SQL", &f));
+    assert!(matches("severity == error", &f));
+    assert!(matches("path == \"payments/api/charge.py\"", &f));
+    assert!(matches("message == 'SEC-003 message'", &f));
+    assert!(!matches("severity == warning", &f));
+}
+
+#[test]
+fn test_globs() {
+    let f = finding("SEC-003", Severity::Error, "payments/api/charge.py");
+    assert!(matches("path ~ \"payments/**\"", &f));
+    assert!(matches("path ~ payments/**", &f));
+    assert!(matches("id ~ SEC-*", &f));
+    assert!(matches("path !~ \"billing/**\"", &f));
+    assert!(!matches("path ~ \"*.ts\"", &f));
+}
+
+#[test]
+fn test_ordered_fields() {
+    let mut f = finding("ERR-001", Severity::Warning, "a.py");
+    f.confidence = Confidence::Medium;
+    assert!(matches("severity >= warning", &f));
+    assert!(matches("severity > info", &f));
+    assert!(!matches("severity > warning", &f));
+    assert!(matches("confidence < high", &f));
+    assert!(matches("confidence >= medium", &f));
+    assert!(!matches("confidence <= low", &f));
+}
+
+#[test]
+fn test_rule_and_module() {
+    let mut f = finding("ERR-001", Severity::Warning, "a.py");
+    assert!(!matches("rule == \"error_handling:swallowed\"", &f));
+    assert!(matches("module != error_handling", &f));
+    f.rule = Some("error_handling:swallowed".to_string());
+    assert!(matches("rule == \"error_handling:swallowed\"", &f));
+    assert!(matches("module == error_handling", &f));
+    assert!(matches("rule ~ \"error_handling:*\"", &f));
+}
+
+#[test]
+fn test_tags() {
+    let mut f = finding("DBG-001", Severity::Info, "gen/api.py");
+    assert!(matches("tag != generated", &f));
+    f.generated = true;
+    f.fix_kind = Some(FixKind::CommentOut);
+    assert!(matches("tag == generated", &f));
+    assert!(matches("tag == fixable", &f));
+    assert!(!matches("tag == ai_false_positive", &f));
+}
+
+#[test]
+fn test_code_kind() {
+    let mut f = finding("SEC-001", Severity::Error, "tests/test_a.py");
+    assert!(matches("code_kind == production", &f));
+    f.code_kind = CodeKind::Test;
+    assert!(matches("code_kind == test and severity == error", &f));
+    assert!(matches("code_kind != script", &f));
+    assert!(parse_error("code_kind == tests").contains("invalid code_kind `tests`"));
+    assert!(parse_error("code_kind > test").contains("code_kind"));
+}
+
+#[test]
+fn test_status() {
+    let f = finding("SEC-001", Severity::Error, "a.py");
+    assert!(matches_with("status == baselined", &f, Status::Baselined));
+    assert!(matches_with("status != new", &f, Status::Suppressed));
+    assert!(!matches_with("status == new", &f, Status::Baselined));
+}
+
+#[test]
+fn test_precedence_not_and_or() {
+    let error = finding("SEC-001", Severity::Error, "payments/a.py");
+    let warning = finding("SQL-001", Severity::Warning, "web/b.py");
+    // and binds tighter than or
+    let filter = "prefix == SQL or prefix == SEC and path ~ \"web/**\"";
+    assert!(!matches(filter, &error));
+    assert!(matches(filter, &warning));
+    let grouped = "(prefix == SQL or prefix == SEC) and path ~ \"web/**\"";
+    assert!(!matches(grouped, &error));
+    assert!(matches(grouped, &warning));
+    // not binds tighter than and
+    assert!(matches("not severity == warning and prefix == SEC", &error));
+    assert!(matches(
+        "not (severity == warning or prefix == SQL)",
+        &error
+    ));
+    assert!(!matches("not not prefix == SQL", &error));
+}
+
+// ── Parse errors ─────────────────────────────────────────────────
+
+#[test]
+fn test_parse_errors_point_at_the_column() {
+    assert_eq!(
+        parse_error("severity == eror"),
+        "column 13: invalid severity `eror`; expected error, warning or info"
+    );
+    assert_eq!(
+        parse_error("severty == error"),
+        "column 1: unknown field `severty`; did you mean `severity`?"
+    );
+    assert_eq!(
+        parse_error("prefix == SEC and colour == red"),
+        "column 19: unknown field `colour`; expected one of: id, prefix, rule, module, severity, confidence, path, status, tag, code_kind, message, tool"
+    );
+    assert_eq!(
+        parse_error("severity = error"),
+        "column 10: `=` is not an operator; use `==`"
+    );
+    assert_eq!(
+        parse_error("path ~ \"payments/**"),
+        "column 8: unterminated string"
+    );
+    assert_eq!(
+        parse_error("(prefix == SEC or prefix == SQL"),
+        "column 1: `(` is never closed"
+    );
+    assert_eq!(
+        parse_error("prefix == SEC)"),
+        "column 14: unexpected `)` without a matching `(`"
+    );
+    assert_eq!(
+        parse_error("prefix == SEC severity == error"),
+        "column 15: expected `and`, `or` or the end of the expression, found `severity`"
+    );
+    assert_eq!(
+        parse_error("prefix == SEC and"),
+        "column 18: expected a field (id, prefix, rule, module, severity, confidence, path, status, tag, code_kind, message, tool), `not` or `(`, found the end of the expression"
+    );
+    assert_eq!(parse_error("   "), "column 4: empty expression");
+}
+
+#[test]
+fn test_operator_errors() {
+    assert_eq!(
+        parse_error("path > b"),
+        "column 6: `>` cannot compare `path`; use ==, !=, ~ or !~"
+    );
+    assert_eq!(
+        parse_error("severity ~ err*"),
+        "column 10: `~` cannot compare `severity`; use ==, !=, <, <=, > or >="
+    );
+    assert_eq!(
+        parse_error("tag < fixable"),
+        "column 5: `<` cannot compare `tag`; use == or !="
+    );
+    assert_eq!(
+        parse_error("tag == vendored"),
+        "column 8: invalid tag `vendored`; expected generated, fixable, ai_false_positive"
+    );
+    assert_eq!(
+        parse_error("status == open"),
+        "column 11: invalid status `open`; expected new, baselined or suppressed"
+    );
+    assert_eq!(
+        parse_error("prefix SEC"),
+        "column 8: expected an operator (==, !=, ~, !~, <, <=, >, >=) after `prefix`, found `SEC`"
+    );
+    assert_eq!(
+        parse_error("prefix =="),
+        "column 10: expected a value after `==`, found the end of the expression"
+    );
+    assert!(parse_error("path ~ \"[a\"").starts_with("column 8: invalid glob `[a`"));
+}
+
+// ── Compile ──────────────────────────────────────────────────────
+
+#[test]
+fn test_compile_defaults() {
+    let compiled =
+        policy::Policy::compile(&policy("name = \"p\"\nfilter = \"prefix == SEC\"")).unwrap();
+    assert_eq!(compiled.action, Action::Fail);
+    assert_eq!(compiled.condition(), "count > 0");
+    let exists = policy::Policy::compile(&policy(
+        "name = \"p\"\nfilter = \"prefix == SEC\"\naggregate = \"exists\"",
+    ))
+    .unwrap();
+    assert_eq!(exists.condition(), "exists");
+}
+
+#[test]
+fn test_compile_errors_name_the_policy() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[[policy]]
+name = "payments"
+filter = "severity == eror"
+
+[[policy]]
+name = "warnings"
+filter = "severity == warning"
+comparator = "=>"
+
+[[policy]]
+name = "warnings"
+filter = "severity == warning"
+action = "block"
+
+[[policy]]
+name = "exists"
+filter = "prefix == SEC"
+aggregate = "exists"
+threshold = 2
+"#,
+    )
+    .unwrap();
+    let errors = policy::compile(&config.policies).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            "[[policy]] \"payments\": filter: column 13: invalid severity `eror`; expected error, warning or info",
+            "[[policy]] \"warnings\": comparator = \"=>\" is invalid. Must be: >, >=, <, <=, ==, !=",
+            "[[policy]] \"warnings\": duplicate policy name",
+            "[[policy]] \"warnings\": action = \"block\" is invalid. Must be: fail, warn, require-escalation",
+            "[[policy]] \"exists\": comparator and threshold only apply to aggregate = \"count\"",
+        ]
+    );
+    let (validation, _) = config.validate();
+    for e in &errors {
+        assert!(validation.contains(e), "{}", e);
+    }
+}
+
+// ── Evaluation ───────────────────────────────────────────────────
+
+fn evaluate(
+    toml: &str,
+    findings: &[Finding],
+    suppressed: &[SuppressedFinding],
+) -> revet_core::PolicyReport {
+    let config: RevetConfig = toml::from_str(toml).unwrap();
+    let policies = policy::compile(&config.policies).unwrap();
+    policy::evaluate(&policies, findings, suppressed, Path::new(ROOT))
+}
+
+#[test]
+fn test_count_threshold() {
+    let toml = r#"
+[[policy]]
+name = "max-5-warnings"
+filter = "severity == warning"
+threshold = 5
+"#;
+    let five: Vec<Finding> = (0..5)
+        .map(|i| finding(&format!("ERR-{:03}", i), Severity::Warning, "a.py"))
+        .collect();
+    let report = evaluate(toml, &five, &[]);
+    assert_eq!(report.results[0].count, 5);
+    assert!(report.results[0].passed);
+    assert!(!report.fails());
+
+    let mut six = five.clone();
+    six.push(finding("ERR-006", Severity::Warning, "b.py"));
+    let report = evaluate(toml, &six, &[]);
+    assert!(!report.results[0].passed);
+    assert!(report.fails());
+    assert_eq!(report.results[0].matches.len(), 6);
+}
+
+#[test]
+fn test_empty_match_passes_except_for_lower_bounds() {
+    let toml = r#"
+[[policy]]
+name = "no-errors"
+filter = "severity == error"
+aggregate = "exists"
+
+[[policy]]
+name = "no-sql"
+filter = "prefix == SQL"
+
+[[policy]]
+name = "needs-a-review-marker"
+filter = "prefix == CUSTOM"
+comparator = "<"
+threshold = 1
+action = "warn"
+"#;
+    let report = evaluate(toml, &[], &[]);
+    assert_eq!(report.results.len(), 3);
+    assert!(report.results.iter().all(|r| r.count == 0));
+    assert!(report.results[0].passed);
+    assert!(report.results[1].passed);
+    // `count < 1` is violated by an empty match
+    assert!(!report.results[2].passed);
+    assert!(!report.fails());
+}
+
+#[test]
+fn test_status_decides_what_is_seen() {
+    let toml = r#"
+[[policy]]
+name = "new-sec"
+filter = "prefix == SEC"
+aggregate = "exists"
+
+[[policy]]
+name = "sec-regardless-of-baseline"
+filter = "prefix == SEC and status != suppressed"
+aggregate = "exists"
+action = "require-escalation"
+"#;
+    let baselined = SuppressedFinding {
+        finding: finding("SEC-001", Severity::Error, "a.py"),
+        reason: "baseline".to_string(),
+    };
+    let inline = SuppressedFinding {
+        finding: finding("SEC-002", Severity::Error, "b.py"),
+        reason: "inline".to_string(),
+    };
+    let report = evaluate(toml, &[], &[baselined, inline]);
+    // Without `status`, only reported findings count
+    assert!(report.results[0].passed);
+    let result = &report.results[1];
+    assert!(!result.passed);
+    assert_eq!(result.matches.len(), 1);
+    assert_eq!(result.matches[0].status, Status::Baselined);
+    assert!(!report.fails());
+    assert_eq!(report.escalations().count(), 1);
+}
+
+#[test]
+fn test_paths_are_relative_to_the_root() {
+    let toml = r#"
+[[policy]]
+name = "payments"
+filter = 'severity == error and path ~ "payments/**"'
+aggregate = "exists"
+"#;
+    let elsewhere = finding("SEC-001", Severity::Error, "web/payments.py");
+    assert!(evaluate(toml, &[elsewhere], &[]).results[0].passed);
+    let inside = finding("SEC-001", Severity::Error, "payments/charge.py");
+    assert!(!evaluate(toml, &[inside], &[]).results[0].passed);
+}
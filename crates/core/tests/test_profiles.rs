@@ -0,0 +1,316 @@
+//! Tests for analysis profile resolution
+
+use revet_core::config::ModulesConfig;
+use revet_core::finding::{Confidence, Finding, Severity};
+use revet_core::{CodeKind, ProfileSettings, RevetConfig, Scope};
+
+fn parse(toml_str: &str) -> RevetConfig {
+    toml::from_str(toml_str).unwrap()
+}
+
+fn resolve(name: Option<&str>, config: &RevetConfig) -> ProfileSettings {
+    ProfileSettings::resolve(name, config).unwrap()
+}
+
+// ── Built-in profiles ────────────────────────────────────────────
+
+#[test]
+fn test_standard_is_default() {
+    let config = RevetConfig::default();
+    let settings = resolve(None, &config);
+
+    assert_eq!(settings.name, "standard");
+    assert_eq!(settings.scope, Scope::Diff);
+    assert_eq!(settings.diff_base, "main");
+    assert!(settings.graph);
+    assert!(!settings.ai);
+    assert_eq!(settings.fail_on, config.general.fail_on);
+    assert_eq!(settings.min_confidence, Confidence::Low);
+    assert!(!settings.history_secrets);
+    assert!(settings.modules.is_empty());
+    assert!(settings.exclude.is_empty());
+    assert!(settings.overrides.is_empty());
+}
+
+#[test]
+fn test_quick_profile() {
+    let settings = resolve(Some("quick"), &RevetConfig::default());
+
+    assert_eq!(settings.scope, Scope::Changed);
+    assert!(!settings.graph);
+    assert!(!settings.ai);
+    assert!(!settings.history_secrets);
+    assert_eq!(settings.modules.get("duplication"), Some(&false));
+}
+
+#[test]
+fn test_deep_profile() {
+    let settings = resolve(Some("deep"), &RevetConfig::default());
+
+    assert_eq!(settings.scope, Scope::Full);
+    assert!(settings.graph);
+    assert!(settings.history_secrets);
+    assert_eq!(settings.modules.get("duplication"), Some(&true));
+    assert_eq!(settings.modules.get("cycles"), Some(&true));
+    assert_eq!(settings.modules.get("dead_code"), Some(&true));
+}
+
+#[test]
+fn test_first_run_profile() {
+    let mut config = RevetConfig::default();
+    let settings = resolve(Some("first-run"), &config);
+    settings.apply(&mut config);
+
+    let enabled: Vec<&str> = ModulesConfig::NAMES
+        .iter()
+        .copied()
+        .filter(|m| settings.modules.get(*m) == Some(&true))
+        .collect();
+    assert_eq!(enabled, ["security", "dead_code", "complexity"]);
+    assert_eq!(settings.modules.len(), ModulesConfig::NAMES.len());
+    assert!(!config.modules.cycles);
+    assert!(config.modules.dead_code);
+    assert_eq!(config.modules.complexity_threshold, 20);
+    assert_eq!(settings.min_confidence, Confidence::High);
+    assert_eq!(settings.min_severity, Severity::Warning);
+    assert_eq!(settings.max_per_rule, Some(5));
+}
+
+#[test]
+fn test_general_profile_selects_default() {
+    let config = parse("[general]\nprofile = \"quick\"\n");
+    assert_eq!(resolve(None, &config).name, "quick");
+    // An explicit name still wins
+    assert_eq!(resolve(Some("deep"), &config).name, "deep");
+}
+
+#[test]
+fn test_base_settings_come_from_general() {
+    let config = parse("[general]\ndiff_base = \"develop\"\nfail_on = \"warning\"\n");
+    let settings = resolve(Some("quick"), &config);
+    assert_eq!(settings.diff_base, "develop");
+    assert_eq!(settings.fail_on, "warning");
+}
+
+// ── Custom profiles ──────────────────────────────────────────────
+
+#[test]
+fn test_custom_profile_overrides_one_key_inherits_rest() {
+    let config = parse(
+        r#"
+[profiles.nightly]
+extends = "deep"
+fail_on = "info"
+"#,
+    );
+    let deep = resolve(Some("deep"), &config);
+    let nightly = resolve(Some("nightly"), &config);
+
+    assert_eq!(nightly.name, "nightly");
+    assert_eq!(nightly.fail_on, "info");
+    assert_eq!(nightly.scope, deep.scope);
+    assert_eq!(nightly.graph, deep.graph);
+    assert_eq!(nightly.ai, deep.ai);
+    assert_eq!(nightly.history_secrets, deep.history_secrets);
+    assert_eq!(nightly.modules, deep.modules);
+    assert_eq!(nightly.min_confidence, deep.min_confidence);
+}
+
+#[test]
+fn test_custom_profile_without_extends_inherits_standard() {
+    let config = parse(
+        r#"
+[profiles.ci]
+min_confidence = "high"
+exclude = ["vendor/**"]
+
+[profiles.ci.modules]
+complexity = false
+"#,
+    );
+    let settings = resolve(Some("ci"), &config);
+
+    assert_eq!(settings.scope, Scope::Diff);
+    assert!(settings.graph);
+    assert_eq!(settings.min_confidence, Confidence::High);
+    assert_eq!(settings.exclude, vec!["vendor/**".to_string()]);
+    assert_eq!(settings.modules.get("complexity"), Some(&false));
+}
+
+#[test]
+fn test_custom_profile_chain() {
+    let config = parse(
+        r#"
+[profiles.base]
+extends = "quick"
+ai = true
+
+[profiles.precommit]
+extends = "base"
+fail_on = "error"
+"#,
+    );
+    let settings = resolve(Some("precommit"), &config);
+
+    assert_eq!(settings.scope, Scope::Changed); // from quick
+    assert!(settings.ai); // from base
+    assert_eq!(settings.fail_on, "error"); // own
+}
+
+#[test]
+fn test_section_named_after_builtin_adjusts_it() {
+    let config = parse("[profiles.quick]\nfail_on = \"never\"\n");
+    let settings = resolve(Some("quick"), &config);
+
+    assert_eq!(settings.fail_on, "never");
+    assert_eq!(settings.scope, Scope::Changed);
+    assert!(!settings.graph);
+}
+
+#[test]
+fn test_module_switch_overrides_parent() {
+    let config = parse(
+        r#"
+[profiles.lean]
+extends = "deep"
+
+[profiles.lean.modules]
+duplication = false
+"#,
+    );
+    let settings = resolve(Some("lean"), &config);
+    assert_eq!(settings.modules.get("duplication"), Some(&false));
+    assert_eq!(settings.modules.get("cycles"), Some(&true));
+}
+
+// ── Errors ───────────────────────────────────────────────────────
+
+#[test]
+fn test_unknown_profile() {
+    let err = ProfileSettings::resolve(Some("turbo"), &RevetConfig::default()).unwrap_err();
+    assert!(err.to_string().contains("unknown profile \"turbo\""));
+    assert!(err.to_string().contains("quick, standard, deep"));
+}
+
+#[test]
+fn test_inheritance_cycle() {
+    let config = parse(
+        r#"
+[profiles.a]
+extends = "b"
+
+[profiles.b]
+extends = "a"
+"#,
+    );
+    let err = ProfileSettings::resolve(Some("a"), &config).unwrap_err();
+    assert!(err.to_string().contains("inherits from itself"));
+}
+
+#[test]
+fn test_unknown_module() {
+    let config = parse("[profiles.x.modules]\nsecurty = false\n");
+    let err = ProfileSettings::resolve(Some("x"), &config).unwrap_err();
+    assert!(err.to_string().contains("unknown module \"securty\""));
+}
+
+#[test]
+fn test_invalid_min_severity() {
+    let config = parse("[profiles.x]\nmin_severity = \"fatal\"\n");
+    let err = ProfileSettings::resolve(Some("x"), &config).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("min_severity = \"fatal\" is invalid"));
+}
+
+#[test]
+fn test_validate_reports_profile_errors() {
+    let config = parse(
+        r#"
+[general]
+profile = "missing"
+
+[profiles.bad]
+fail_on = "sometimes"
+"#,
+    );
+    let (errors, _) = config.validate();
+    assert!(errors.iter().any(|e| e.starts_with("[profiles.bad]")));
+    assert!(errors.iter().any(|e| e.starts_with("[general] profile:")));
+}
+
+// ── Applying settings ────────────────────────────────────────────
+
+#[test]
+fn test_apply_writes_into_config() {
+    let mut config = parse(
+        r#"
+[profiles.ci]
+extends = "deep"
+fail_on = "warning"
+exclude = ["generated/**"]
+"#,
+    );
+    let settings = resolve(Some("ci"), &config);
+    settings.apply(&mut config);
+
+    assert!(config.modules.duplication);
+    assert!(config.modules.cycles);
+    assert!(config.modules.dead_code);
+    assert_eq!(config.general.fail_on, "warning");
+    assert!(config.ignore.paths.contains(&"generated/**".to_string()));
+}
+
+#[test]
+fn test_keeps_applies_confidence_floor() {
+    let config = parse("[profiles.strict]\nmin_confidence = \"medium\"\n");
+    let settings = resolve(Some("strict"), &config);
+
+    let finding = |confidence| Finding {
+        confidence,
+        ..Default::default()
+    };
+    assert!(settings.keeps(&finding(Confidence::High)));
+    assert!(settings.keeps(&finding(Confidence::Medium)));
+    assert!(!settings.keeps(&finding(Confidence::Low)));
+}
+
+#[test]
+fn test_keeps_applies_severity_floor() {
+    let config = parse("[profiles.loud]\nmin_severity = \"warning\"\n");
+    let settings = resolve(Some("loud"), &config);
+
+    let finding = |severity| Finding {
+        severity,
+        ..Default::default()
+    };
+    assert!(settings.keeps(&finding(Severity::Error)));
+    assert!(settings.keeps(&finding(Severity::Warning)));
+    assert!(!settings.keeps(&finding(Severity::Info)));
+}
+
+#[test]
+fn test_keeps_applies_only() {
+    let mut settings = resolve(None, &RevetConfig::default());
+    let finding = |code_kind| Finding {
+        code_kind,
+        ..Default::default()
+    };
+    assert!(settings.keeps(&finding(CodeKind::Test)));
+
+    settings.only = Some(CodeKind::Production);
+    assert!(settings.keeps(&finding(CodeKind::Production)));
+    assert!(!settings.keeps(&finding(CodeKind::Test)));
+    assert!(!settings.keeps(&finding(CodeKind::Script)));
+}
+
+#[test]
+fn test_reproducibility_records_profile_and_overrides() {
+    let mut settings = resolve(Some("quick"), &RevetConfig::default());
+    settings.record_override("--fail-on error");
+
+    let repro = settings.reproducibility();
+    assert_eq!(repro.revet_version, revet_core::VERSION);
+    assert_eq!(repro.profile, "quick");
+    assert_eq!(repro.overrides, vec!["--fail-on error".to_string()]);
+}
@@ -0,0 +1,402 @@
+//! Tests for Python framework entry points and injected dependencies
+//! (`[parser.python.frameworks]`)
+
+use revet_core::analyzer::unused_exports::UnusedExportsAnalyzer;
+use revet_core::analyzer::GraphAnalyzer;
+use revet_core::config::RevetConfig;
+use revet_core::discovery::discover_files;
+use revet_core::graph::{EdgeKind, EdgeMetadata, NodeId, NodeKind};
+use revet_core::parser::python_frameworks::{entry_point, Frameworks};
+use revet_core::{CodeGraph, Confidence, ParserDispatcher};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn copy_tree(from: &Path, to: &Path) {
+    for entry in std::fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            std::fs::create_dir_all(&target).unwrap();
+            copy_tree(&entry.path(), &target);
+        } else {
+            std::fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+fn parse(root: &Path, config: &RevetConfig) -> CodeGraph {
+    let dispatcher = ParserDispatcher::new_with_config(config);
+    let files = discover_files(root, &dispatcher.supported_extensions(), &[]).unwrap();
+    let (graph, errors) = dispatcher.parse_files_parallel(&files, root.to_path_buf());
+    assert!(errors.is_empty(), "errors: {:?}", errors);
+    graph
+}
+
+/// The fixture, copied out of the repository's own `tests/` directory so
+/// its application code is not mistaken for tests
+fn fixture() -> (TempDir, CodeGraph, RevetConfig) {
+    let dir = TempDir::new().unwrap();
+    copy_tree(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/fastapi_app"),
+        dir.path(),
+    );
+    let config = RevetConfig::from_file(&dir.path().join(".revet.toml")).unwrap();
+    let graph = parse(dir.path(), &config);
+    (dir, graph, config)
+}
+
+fn repo(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (name, content) in files {
+        let path = dir.path().join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+fn function(graph: &CodeGraph, name: &str) -> NodeId {
+    let found: Vec<NodeId> = graph
+        .nodes()
+        .filter(|(_, n)| n.kind() == &NodeKind::Function && n.name() == name)
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(found.len(), 1, "functions named {}", name);
+    found[0]
+}
+
+/// Names of the functions `name` references through the frameworks, with
+/// the confidence of each reference
+fn references(graph: &CodeGraph, name: &str) -> Vec<(String, Confidence)> {
+    let mut refs: Vec<(String, Confidence)> = graph
+        .edges_from(function(graph, name))
+        .filter_map(|(target, e)| match e.metadata() {
+            Some(EdgeMetadata::Reference { confidence, .. }) => {
+                assert_eq!(e.kind(), &EdgeKind::References);
+                Some((graph.node(target)?.name().to_string(), *confidence))
+            }
+            _ => None,
+        })
+        .collect();
+    refs.sort_by(|a, b| a.0.cmp(&b.0));
+    refs
+}
+
+fn dependents(graph: &CodeGraph, name: &str) -> Vec<String> {
+    let mut names: Vec<String> = graph
+        .query()
+        .direct_dependents(function(graph, name))
+        .into_iter()
+        .filter_map(|id| graph.node(id))
+        .filter(|n| n.kind() == &NodeKind::Function)
+        .map(|n| n.name().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+fn unused(graph: &CodeGraph, config: &RevetConfig) -> Vec<String> {
+    let mut names: Vec<String> = UnusedExportsAnalyzer::new()
+        .analyze_graph(graph, config)
+        .iter()
+        .map(|f| {
+            let start = f.message.find('`').unwrap() + 1;
+            let end = start + f.message[start..].find('`').unwrap();
+            f.message[start..end].to_string()
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+fn high(names: &[&str]) -> Vec<(String, Confidence)> {
+    names
+        .iter()
+        .map(|n| (n.to_string(), Confidence::High))
+        .collect()
+}
+
+// ── Fixture ──────────────────────────────────────────────────────
+
+#[test]
+fn test_fixture_has_no_false_unused_exports() {
+    let (_, graph, config) = fixture();
+    assert_eq!(unused(&graph, &config), vec!["legacy_lookup"]);
+}
+
+#[test]
+fn test_fixture_entry_points() {
+    let (_, graph, _) = fixture();
+    let framework =
+        |name: &str| entry_point(graph.node(function(&graph, name)).unwrap()).map(str::to_string);
+    for route in [
+        "read_user",
+        "read_me",
+        "create_order",
+        "warm_up",
+        "value_error_handler",
+    ] {
+        assert_eq!(framework(route).as_deref(), Some("fastapi"), "{}", route);
+    }
+    for task in ["send_receipt", "purge_expired_orders"] {
+        assert_eq!(framework(task).as_deref(), Some("celery"), "{}", task);
+    }
+    for fixture in ["db", "user_fixture", "client"] {
+        assert_eq!(framework(fixture).as_deref(), Some("pytest"), "{}", fixture);
+    }
+    for plain in [
+        "get_db",
+        "get_current_user",
+        "render_receipt",
+        "test_read_user",
+    ] {
+        assert_eq!(framework(plain), None, "{}", plain);
+    }
+}
+
+#[test]
+fn test_fixture_dependency_edges() {
+    let (_, graph, _) = fixture();
+    assert_eq!(references(&graph, "read_user"), high(&["get_db"]));
+    assert_eq!(
+        references(&graph, "read_me"),
+        high(&["get_current_user", "verify_token"])
+    );
+    assert_eq!(
+        references(&graph, "create_order"),
+        high(&["get_current_user", "get_db"])
+    );
+    assert_eq!(
+        references(&graph, "get_current_user"),
+        high(&["get_db", "verify_token"])
+    );
+    assert_eq!(
+        dependents(&graph, "get_db"),
+        vec!["create_order", "get_current_user", "read_user"]
+    );
+}
+
+#[test]
+fn test_fixture_pytest_fixture_edges() {
+    let (_, graph, _) = fixture();
+    let medium = |names: &[&str]| -> Vec<(String, Confidence)> {
+        names
+            .iter()
+            .map(|n| (n.to_string(), Confidence::Medium))
+            .collect()
+    };
+    assert_eq!(
+        references(&graph, "test_read_user"),
+        medium(&["client", "user_fixture"])
+    );
+    // `tmp_path` is pytest's own fixture
+    assert_eq!(references(&graph, "test_read_me"), medium(&["client"]));
+    assert_eq!(references(&graph, "client"), medium(&["db"]));
+    assert_eq!(references(&graph, "user_fixture"), medium(&["db"]));
+}
+
+#[test]
+fn test_linking_is_idempotent() {
+    let (dir, mut graph, _) = fixture();
+    let count = |graph: &CodeGraph| graph.inner_graph().edge_count();
+    let before = count(&graph);
+    let decorators = graph
+        .node(function(&graph, "read_user"))
+        .unwrap()
+        .decorators()
+        .to_vec();
+    revet_core::parser::python_frameworks::link(&mut graph, &Frameworks::builtin(), dir.path());
+    assert_eq!(count(&graph), before);
+    assert_eq!(
+        graph
+            .node(function(&graph, "read_user"))
+            .unwrap()
+            .decorators(),
+        decorators.as_slice()
+    );
+}
+
+// ── Built-in frameworks ──────────────────────────────────────────
+
+#[test]
+fn test_builtin_decorators() {
+    let frameworks = Frameworks::builtin();
+    let cases = [
+        ("app.route(\"/\", methods=[\"POST\"])", Some("flask")),
+        ("bp.errorhandler(404)", Some("flask")),
+        ("router.get(\"/users\")", Some("fastapi")),
+        ("app.on_event(\"startup\")", Some("fastapi")),
+        ("celery.task(bind=True)", Some("celery")),
+        ("shared_task", Some("celery")),
+        ("click.command()", Some("click")),
+        ("cli.group()", Some("click")),
+        ("pytest.fixture(scope=\"session\")", Some("pytest")),
+        ("property", None),
+        ("functools.lru_cache(maxsize=None)", None),
+        ("staticmethod", None),
+    ];
+    for (decorator, expected) in cases {
+        assert_eq!(frameworks.registering(decorator), expected, "{}", decorator);
+    }
+}
+
+#[test]
+fn test_flask_and_click_are_roots() {
+    let dir = repo(&[(
+        "app.py",
+        r#"import click
+from flask import Flask
+
+app = Flask(__name__)
+
+
+@app.route("/health")
+def health():
+    return "ok"
+
+
+@click.command()
+def seed():
+    pass
+
+
+def unused_helper():
+    pass
+"#,
+    )]);
+    let config = RevetConfig::default();
+    let graph = parse(dir.path(), &config);
+    assert_eq!(unused(&graph, &config), vec!["unused_helper"]);
+}
+
+// ── Configuration ────────────────────────────────────────────────
+
+const JOBS: &str = r#"from jobs import Inject, register
+
+
+def load_settings():
+    return {}
+
+
+@register("nightly")
+def nightly(settings=Inject(load_settings)):
+    pass
+"#;
+
+#[test]
+fn test_configured_framework() {
+    let dir = repo(&[("jobs_app.py", JOBS)]);
+    let config: RevetConfig = toml::from_str(
+        r#"
+[parser.python.frameworks.jobs]
+decorators   = ["register"]
+dependencies = ["Inject"]
+"#,
+    )
+    .unwrap();
+    let graph = parse(dir.path(), &config);
+    assert!(unused(&graph, &config).is_empty());
+    assert_eq!(
+        entry_point(graph.node(function(&graph, "nightly")).unwrap()),
+        Some("jobs")
+    );
+    assert_eq!(references(&graph, "nightly"), high(&["load_settings"]));
+
+    // Without the entry, both functions look unused
+    let config = RevetConfig::default();
+    let graph = parse(dir.path(), &config);
+    assert_eq!(unused(&graph, &config), vec!["load_settings", "nightly"]);
+}
+
+#[test]
+fn test_configured_entry_replaces_builtin() {
+    let dir = repo(&[(
+        "tasks.py",
+        "from celery import shared_task\n\n\n@shared_task\ndef purge():\n    pass\n",
+    )]);
+    let config: RevetConfig =
+        toml::from_str("[parser.python.frameworks.celery]\ndecorators = []\n").unwrap();
+    let graph = parse(dir.path(), &config);
+    assert_eq!(unused(&graph, &config), vec!["purge"]);
+}
+
+#[test]
+fn test_config_validation() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[parser.python.frameworks.jobs]
+decorators   = ["@register", "jobs.*", "tasks.run()"]
+dependencies = ["Inject", "Provide*"]
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    let errors: Vec<&String> = errors
+        .iter()
+        .filter(|e| e.starts_with("[parser.python.frameworks.jobs]"))
+        .collect();
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors[0].contains("decorators entry \"@register\""));
+    assert!(errors[1].contains("decorators entry \"tasks.run()\""));
+    assert!(errors[2].contains("dependencies entry \"Provide*\""));
+}
+
+// ── Fixture scoping ──────────────────────────────────────────────
+
+#[test]
+fn test_fixtures_are_package_scoped() {
+    let dir = repo(&[
+        (
+            "tests/conftest.py",
+            "import pytest\n\n\n@pytest.fixture\ndef db():\n    pass\n",
+        ),
+        (
+            "tests/api/conftest.py",
+            "import pytest\n\n\n@pytest.fixture\ndef db(db):\n    return db\n",
+        ),
+        ("tests/api/test_a.py", "def test_a(db):\n    assert db\n"),
+        ("tests/test_b.py", "def test_b(db):\n    assert db\n"),
+        (
+            "other/conftest.py",
+            "import pytest\n\n\n@pytest.fixture\ndef cache():\n    pass\n",
+        ),
+        ("tests/test_c.py", "def test_c(cache):\n    assert cache\n"),
+    ]);
+    let graph = parse(dir.path(), &RevetConfig::default());
+    let target = |test: &str| -> Vec<PathBuf> {
+        graph
+            .edges_from(function(&graph, test))
+            .filter(|(_, e)| e.kind() == &EdgeKind::References)
+            .map(|(t, _)| {
+                graph
+                    .node(t)
+                    .unwrap()
+                    .file_path()
+                    .strip_prefix(dir.path())
+                    .unwrap()
+                    .to_path_buf()
+            })
+            .collect()
+    };
+    // The nearest conftest wins
+    assert_eq!(
+        target("test_a"),
+        vec![PathBuf::from("tests/api/conftest.py")]
+    );
+    assert_eq!(target("test_b"), vec![PathBuf::from("tests/conftest.py")]);
+    // Fixtures of unrelated directories are out of scope
+    assert!(target("test_c").is_empty());
+
+    // An overriding fixture requests the one it overrides
+    let overriding = graph
+        .nodes()
+        .find(|(_, n)| n.name() == "db" && n.file_path().ends_with("api/conftest.py"))
+        .map(|(id, _)| id)
+        .unwrap();
+    let overridden: Vec<&Path> = graph
+        .edges_from(overriding)
+        .filter(|(_, e)| e.kind() == &EdgeKind::References)
+        .map(|(t, _)| graph.node(t).unwrap().file_path().as_path())
+        .collect();
+    assert_eq!(overridden, vec![dir.path().join("tests/conftest.py")]);
+}
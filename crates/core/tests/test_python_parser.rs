@@ -385,6 +385,53 @@ async def main():
     assert!(function_names.contains(&"main"));
 }
 
+#[test]
+fn test_parse_decorators() {
+    let source = r#"
+@router.get(
+    "/users/{user_id}",
+    dependencies=[Depends(verify_token)],
+)
+@cache
+def read_user(user_id: int):
+    pass
+
+class Service:
+    @staticmethod
+    def build():
+        pass
+
+def undecorated():
+    pass
+"#;
+
+    let mut graph = CodeGraph::new(PathBuf::from("/test"));
+    let dispatcher = ParserDispatcher::new();
+    let parser = dispatcher.find_parser(&PathBuf::from("test.py")).unwrap();
+    parser
+        .parse_source(source, &PathBuf::from("test.py"), &mut graph)
+        .unwrap();
+
+    let decorators = |name: &str| -> Vec<String> {
+        graph
+            .nodes()
+            .find(|(_, n)| n.name() == name)
+            .unwrap()
+            .1
+            .decorators()
+            .to_vec()
+    };
+    assert_eq!(
+        decorators("read_user"),
+        vec![
+            "router.get( \"/users/{user_id}\", dependencies=[Depends(verify_token)], )",
+            "cache"
+        ]
+    );
+    assert_eq!(decorators("build"), vec!["staticmethod"]);
+    assert!(decorators("undecorated").is_empty());
+}
+
 #[test]
 fn test_graph_statistics() {
     let source = r#"
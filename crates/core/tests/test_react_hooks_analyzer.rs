@@ -3,7 +3,7 @@
 use revet_core::analyzer::react_hooks::ReactHooksAnalyzer;
 use revet_core::analyzer::Analyzer;
 use revet_core::config::RevetConfig;
-use revet_core::finding::Severity;
+use revet_core::finding::{InsertContext, Severity};
 use std::path::PathBuf;
 use tempfile::TempDir;
 
@@ -345,3 +345,71 @@ function App() {
     assert_eq!(hooks_findings[1].id, "HOOKS-002");
     assert_eq!(hooks_findings[2].id, "HOOKS-003");
 }
+
+// ── Suggested code ──────────────────────────────────────────────
+
+#[test]
+fn test_effect_without_deps_suggests_dependency_array() {
+    let dir = TempDir::new().unwrap();
+    let file = write_temp_file(
+        &dir,
+        "App.tsx",
+        r#"
+function App() {
+  useEffect(() => {
+    load(")");
+  });
+  return <div />;
+}
+"#,
+    );
+
+    let findings = ReactHooksAnalyzer::new().analyze_files(&[file], dir.path());
+    let code = findings[0].suggestion_code.as_ref().unwrap();
+    assert_eq!(code.language, "tsx");
+    assert_eq!(
+        code.snippet,
+        "useEffect(() => {\n    load(\")\");\n  }, [])"
+    );
+    assert_eq!(
+        code.insert_context,
+        InsertContext::Replace {
+            original: "useEffect(() => {\n    load(\")\");\n  })".to_string()
+        }
+    );
+    assert!(!findings[0].suggestion.as_ref().unwrap().contains("=>"));
+}
+
+#[test]
+fn test_missing_key_suggests_keyed_element() {
+    let dir = TempDir::new().unwrap();
+    let file = write_temp_file(
+        &dir,
+        "List.jsx",
+        "const List = ({ items }) => (\n  <ul>{items.map((item, i) => <Row name={item.name} />)}</ul>\n);\n",
+    );
+
+    let findings = ReactHooksAnalyzer::new().analyze_files(&[file], dir.path());
+    let code = findings[0].suggestion_code.as_ref().unwrap();
+    assert_eq!(code.language, "javascript");
+    assert_eq!(
+        code.snippet,
+        "<ul>{items.map((item, i) => <Row key={item.id} name={item.name} />)}</ul>"
+    );
+    assert_eq!(code.insert_context, InsertContext::Line);
+}
+
+#[test]
+fn test_destructured_map_callback_has_no_code() {
+    let dir = TempDir::new().unwrap();
+    let file = write_temp_file(
+        &dir,
+        "List.tsx",
+        "const rows = items.map(({ id }) => <Row id={id} />);\n",
+    );
+
+    let findings = ReactHooksAnalyzer::new().analyze_files(&[file], dir.path());
+    assert_eq!(findings.len(), 1);
+    assert!(findings[0].suggestion_code.is_none());
+    assert!(findings[0].suggestion.is_some());
+}
@@ -0,0 +1,271 @@
+//! Integration tests for refactor verification (`[modules] refactor = true`)
+
+use git2::{IndexAddOption, Repository, Signature};
+use revet_core::analyzer::complexity::delta::DeltaBase;
+use revet_core::analyzer::refactor;
+use revet_core::{CodeGraph, Finding, GitObjects, ParserDispatcher};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/refactor_verification")
+}
+
+fn copy_tree(from: &Path, to: &Path) {
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            fs::create_dir_all(&target).unwrap();
+            copy_tree(&entry.path(), &target);
+        } else {
+            fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+fn commit_all(repo: &Repository) {
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"].iter(), IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = Signature::now("dev", "dev@example.com").unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "base", &tree, &[])
+        .unwrap();
+}
+
+/// A repository with `base` committed and `head` in the working tree, both
+/// given as (path, source) pairs
+fn repo(base: &[(&str, &str)], head: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    for (path, source) in base {
+        fs::write(dir.path().join(path), source).unwrap();
+    }
+    commit_all(&repo);
+    for (path, source) in head {
+        fs::write(dir.path().join(path), source).unwrap();
+    }
+    dir
+}
+
+/// The fixture as a repository: `base/` committed, `head/` in the working tree
+fn fixture_repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    copy_tree(&fixture_dir().join("base"), dir.path());
+    commit_all(&repo);
+    copy_tree(&fixture_dir().join("head"), dir.path());
+    dir
+}
+
+/// The head graph of every file in the repository
+fn head_graph(root: &Path) -> CodeGraph {
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                if !path.ends_with(".git") {
+                    dirs.push(path);
+                }
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    ParserDispatcher::new()
+        .parse_files_parallel(&files, root.to_path_buf())
+        .0
+}
+
+fn analyze(dir: &TempDir) -> Vec<Finding> {
+    let root = dir.path().canonicalize().unwrap();
+    let base = DeltaBase::at_ref(
+        &root,
+        "HEAD",
+        &ParserDispatcher::new(),
+        &GitObjects::default(),
+    )
+    .unwrap();
+    refactor::analyze(&head_graph(&root), &base)
+}
+
+/// (file, line, sub-rule) of each finding, sorted
+fn rows(findings: &[Finding]) -> Vec<(String, usize, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .map(|f| {
+            let file = f.file.file_name().unwrap().to_string_lossy().to_string();
+            (file, f.line, f.rule.clone().unwrap_or_default())
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+// ── Fixture ──────────────────────────────────────────────────────
+
+#[test]
+fn test_fixture_reports_missed_callers() {
+    let dir = fixture_repo();
+    let findings = analyze(&dir);
+    assert_eq!(
+        rows(&findings),
+        vec![
+            ("invoice.ts".to_string(), 4, "arity_mismatch".to_string()),
+            ("refunds.py".to_string(), 1, "stale_reference".to_string()),
+            ("refunds.py".to_string(), 5, "arity_mismatch".to_string()),
+        ]
+    );
+    let python = findings
+        .iter()
+        .find(|f| f.line == 5)
+        .map(|f| f.message.as_str());
+    assert_eq!(
+        python,
+        Some(
+            "Call to `charge` passes 1 argument(s), but this change made its signature \
+             `charge(order, currency)` (was `charge(order)`)"
+        )
+    );
+}
+
+#[test]
+fn test_fixture_findings_are_errors() {
+    let dir = fixture_repo();
+    for finding in analyze(&dir) {
+        assert_eq!(finding.severity, revet_core::Severity::Error);
+        assert!(finding.suggestion.is_some());
+    }
+}
+
+// ── Arity ────────────────────────────────────────────────────────
+
+#[test]
+fn test_new_parameter_with_default_is_not_reported() {
+    let dir = repo(
+        &[
+            ("lib.py", "def f(a):\n    return a\n"),
+            (
+                "app.py",
+                "from lib import f\n\n\ndef g():\n    return f(1)\n",
+            ),
+        ],
+        &[("lib.py", "def f(a, b=2):\n    return a + b\n")],
+    );
+    assert!(analyze(&dir).is_empty());
+}
+
+#[test]
+fn test_variadic_signature_is_skipped() {
+    let dir = repo(
+        &[
+            ("lib.py", "def f(a):\n    return a\n"),
+            (
+                "app.py",
+                "from lib import f\n\n\ndef g():\n    return f(1)\n",
+            ),
+        ],
+        &[("lib.py", "def f(a, b, *rest):\n    return a\n")],
+    );
+    assert!(analyze(&dir).is_empty());
+}
+
+#[test]
+fn test_removed_parameter_is_reported() {
+    let dir = repo(
+        &[
+            ("lib.py", "def f(a, b):\n    return a + b\n"),
+            (
+                "app.py",
+                "from lib import f\n\n\ndef g():\n    return f(1, 2)\n",
+            ),
+        ],
+        &[("lib.py", "def f(a):\n    return a\n")],
+    );
+    assert_eq!(
+        rows(&analyze(&dir)),
+        vec![("app.py".to_string(), 5, "arity_mismatch".to_string())]
+    );
+}
+
+#[test]
+fn test_spread_arguments_are_skipped() {
+    let dir = repo(
+        &[
+            ("lib.ts", "export function f(a: number): number {\n  return a;\n}\n"),
+            (
+                "app.ts",
+                "import { f } from \"./lib\";\n\nexport function g(xs: [number, number]): number {\n  return f(...xs);\n}\n",
+            ),
+        ],
+        &[(
+            "lib.ts",
+            "export function f(a: number, b: number): number {\n  return a + b;\n}\n",
+        )],
+    );
+    assert!(analyze(&dir).is_empty());
+}
+
+// ── Renames ──────────────────────────────────────────────────────
+
+#[test]
+fn test_rename_reports_calls_left_in_the_same_file() {
+    let dir = repo(
+        &[(
+            "lib.py",
+            "def old(a):\n    return a\n\n\ndef user():\n    return old(1)\n",
+        )],
+        &[(
+            "lib.py",
+            "def new(a):\n    return a\n\n\ndef user():\n    return old(1)\n",
+        )],
+    );
+    let findings = analyze(&dir);
+    assert_eq!(
+        rows(&findings),
+        vec![("lib.py".to_string(), 6, "stale_reference".to_string())]
+    );
+    assert_eq!(
+        findings[0].message,
+        "`old` no longer exists: this change renamed it to `new` in lib.py"
+    );
+}
+
+#[test]
+fn test_rename_with_updated_callers_is_clean() {
+    let dir = repo(
+        &[
+            ("lib.py", "def old(a):\n    return a\n"),
+            (
+                "app.py",
+                "from lib import old\n\n\ndef g():\n    return old(1)\n",
+            ),
+        ],
+        &[
+            ("lib.py", "def new(a):\n    return a\n"),
+            (
+                "app.py",
+                "from lib import new\n\n\ndef g():\n    return new(1)\n",
+            ),
+        ],
+    );
+    assert!(analyze(&dir).is_empty());
+}
+
+#[test]
+fn test_different_parameters_are_not_a_rename() {
+    let dir = repo(
+        &[
+            ("lib.py", "def old(a):\n    return a\n"),
+            ("app.py", "from lib import old\n"),
+        ],
+        &[("lib.py", "def new(a, b):\n    return a + b\n")],
+    );
+    assert!(analyze(&dir).is_empty());
+}
@@ -0,0 +1,302 @@
+//! Tests for finding-ID namespaces (`analyzer::registry`)
+
+use revet_core::analyzer::registry::{self, prefix_of, Registry, Source, BUILTIN};
+use revet_core::analyzer::rules;
+use revet_core::config::{ModulesConfig, RevetConfig};
+use revet_core::discovery::discover_files_extended;
+use revet_core::{AnalyzerDispatcher, CodeGraph, ParserDispatcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn config(toml: &str) -> RevetConfig {
+    toml::from_str(toml).unwrap()
+}
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures")
+        .join(name)
+        .canonicalize()
+        .unwrap()
+}
+
+// ── Built-in namespaces ──────────────────────────────────────────
+
+#[test]
+fn test_builtin_prefixes_are_unique() {
+    let mut seen = HashSet::new();
+    for builtin in BUILTIN {
+        assert!(
+            seen.insert(builtin.prefix),
+            "{} listed twice",
+            builtin.prefix
+        );
+        assert!(registry::check_custom_prefix(builtin.prefix).is_err());
+    }
+    assert!(!seen.contains(registry::CUSTOM));
+}
+
+#[test]
+fn test_every_analyzer_prefix_is_registered() {
+    let registry = Registry::new();
+    let dispatcher = AnalyzerDispatcher::new_with_config(&RevetConfig::default());
+    let prefixes: Vec<&str> = dispatcher.prefixes().collect();
+    assert_eq!(
+        prefixes.len(),
+        prefixes.iter().collect::<HashSet<_>>().len(),
+        "two analyzers share a prefix: {:?}",
+        prefixes
+    );
+    for prefix in prefixes {
+        let claim = registry.get(prefix).unwrap_or_else(|| panic!("{}", prefix));
+        assert_eq!(claim.source, Source::Builtin, "{}", prefix);
+    }
+}
+
+#[test]
+fn test_builtin_rules_leave_custom_namespace_free() {
+    for (module, sub_rules) in rules::catalog() {
+        for rule in sub_rules {
+            assert!(!rule.starts_with("custom-"), "{}:{}", module, rule);
+        }
+    }
+}
+
+#[test]
+fn test_emitted_ids_resolve_to_registered_rules() {
+    let mut config = RevetConfig::default();
+    for name in ModulesConfig::NAMES {
+        config.modules.set(name, true);
+    }
+    let registry = Registry::from_config(&config).unwrap();
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config);
+    let parsers = ParserDispatcher::new();
+
+    let mut checked = 0;
+    for name in [
+        "python_flask_app",
+        "typescript_express",
+        "ml_pipeline",
+        "terraform_project",
+        "kubernetes_project",
+        "skew_monorepo",
+        "i18n_app",
+    ] {
+        let root = fixture(name);
+        let mut extensions = parsers.supported_extensions();
+        extensions.extend(dispatcher.extra_extensions(&config));
+        let files = discover_files_extended(
+            &root,
+            &extensions,
+            &dispatcher.extra_filenames(&config),
+            &[],
+        )
+        .unwrap();
+        let mut graph = CodeGraph::new(root.clone());
+        for file in &files {
+            let _ = parsers.parse_file(file, &mut graph);
+        }
+        let mut findings = dispatcher.run_all(&files, &root, &config);
+        findings.extend(dispatcher.run_graph_analyzers(&graph, &config));
+
+        for finding in findings {
+            let claim = registry
+                .resolve(&finding.id)
+                .unwrap_or_else(|| panic!("{} in {} is unregistered", finding.id, name));
+            assert_eq!(
+                claim.source,
+                registry::source_of(&finding),
+                "{}",
+                finding.id
+            );
+            if let Some(rule) = &finding.rule {
+                assert!(registry.rule(rule).is_some(), "{} ({})", rule, finding.id);
+            }
+            checked += 1;
+        }
+    }
+    assert!(checked > 50, "only {} findings checked", checked);
+}
+
+// ── IDs and sources ──────────────────────────────────────────────
+
+#[test]
+fn test_prefix_of() {
+    assert_eq!(prefix_of("SEC-001"), "SEC");
+    assert_eq!(prefix_of("K8S-012"), "K8S");
+    assert_eq!(prefix_of("SEC"), "SEC");
+    assert_eq!(prefix_of("PLUGIN-crypto-003"), "PLUGIN-crypto");
+    assert_eq!(prefix_of("PLUGIN-crypto"), "PLUGIN-crypto");
+}
+
+#[test]
+fn test_source_round_trip() {
+    for source in [
+        Source::Builtin,
+        Source::Custom,
+        Source::Plugin("crypto".to_string()),
+    ] {
+        let json = serde_json::to_string(&source).unwrap();
+        assert_eq!(serde_json::from_str::<Source>(&json).unwrap(), source);
+    }
+    assert_eq!(
+        serde_json::to_string(&Source::Plugin("crypto".to_string())).unwrap(),
+        r#""plugin:crypto""#
+    );
+    assert!(serde_json::from_str::<Source>(r#""plugin:""#).is_err());
+    assert!(serde_json::from_str::<Source>(r#""vendor""#).is_err());
+}
+
+// ── Claims ───────────────────────────────────────────────────────
+
+#[test]
+fn test_custom_rule_cannot_take_builtin_prefix() {
+    let config = config(
+        r#"
+[[rules]]
+id = "no-secrets"
+prefix = "SEC"
+pattern = "password"
+message = "password"
+"#,
+    );
+    let errors = Registry::from_config(&config).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            "[[rules]] \"no-secrets\" prefix \"SEC\": is reserved for Secret exposure (builtin)"
+                .to_string()
+        ]
+    );
+    assert!(config.validate().0.contains(&errors[0]));
+}
+
+#[test]
+fn test_custom_prefix_must_be_well_formed() {
+    for prefix in ["acme", "ACME-X", "9LIVES", "PLUGINX", ""] {
+        assert!(
+            registry::check_custom_prefix(prefix).is_err(),
+            "{:?} accepted",
+            prefix
+        );
+    }
+    assert!(registry::check_custom_prefix("ACME2").is_ok());
+}
+
+#[test]
+fn test_custom_rules_share_a_prefix() {
+    let config = config(
+        r#"
+[[rules]]
+id = "no-todo"
+prefix = "ACME"
+pattern = "TODO"
+message = "TODO"
+
+[[rules]]
+id = "no-fixme"
+prefix = "ACME"
+pattern = "FIXME"
+message = "FIXME"
+"#,
+    );
+    let registry = Registry::from_config(&config).unwrap();
+    let claim = registry.get("ACME").unwrap();
+    assert_eq!(claim.source, Source::Custom);
+    assert_eq!(claim.claimant, "[[rules]] \"no-todo\"");
+}
+
+#[test]
+fn test_plugin_namespace() {
+    let mut registry = Registry::new();
+    let plugin = || Source::Plugin("crypto".to_string());
+    registry
+        .claim_prefix("PLUGIN-crypto", "crypto plugin", plugin())
+        .unwrap();
+    registry
+        .claim_prefix("PLUGIN-crypto", "crypto plugin", plugin())
+        .unwrap();
+    assert_eq!(
+        registry
+            .claim_prefix("PLUGIN-crypto", "hashes plugin", plugin())
+            .unwrap_err(),
+        "finding prefix \"PLUGIN-crypto\" is claimed by both crypto plugin (plugin:crypto) \
+         and hashes plugin (plugin:crypto)"
+    );
+    assert_eq!(
+        registry
+            .claim_prefix("SEC", "crypto plugin", plugin())
+            .unwrap_err(),
+        "crypto plugin prefix \"SEC\": plugins must use \"PLUGIN-crypto\""
+    );
+    assert!(registry
+        .claim_prefix("PLUGIN-crypto", "crypto rules", Source::Custom)
+        .is_err());
+}
+
+#[test]
+fn test_sub_rule_conflict_names_both_claimants() {
+    let mut registry = Registry::new();
+    let error = registry
+        .claim_rule(
+            "security",
+            "custom-acme",
+            "acme plugin",
+            Source::Plugin("acme".into()),
+        )
+        .and_then(|_| {
+            registry.claim_rule(
+                "security",
+                "custom-acme",
+                "[[analyzers.secrets.patterns]] \"acme\"",
+                Source::Custom,
+            )
+        })
+        .unwrap_err();
+    assert_eq!(
+        error,
+        "sub-rule \"security:custom-acme\" is claimed by both acme plugin (plugin:acme) \
+         and [[analyzers.secrets.patterns]] \"acme\" (custom)"
+    );
+    assert!(registry
+        .claim_rule("error_handling", "unwrap", "x", Source::Custom)
+        .is_err());
+}
+
+// ── Custom prefixes at run time ──────────────────────────────────
+
+#[test]
+fn test_custom_prefix_findings() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("app.py"), "# TODO: one\nx = 1  # FIXME\n").unwrap();
+    let config = config(
+        r#"
+[[rules]]
+id = "no-todo"
+pattern = "TODO"
+message = "TODO left in code"
+
+[[rules]]
+id = "no-fixme"
+prefix = "ACME"
+pattern = "FIXME"
+message = "FIXME left in code"
+
+[[rules]]
+id = "shadow"
+prefix = "SEC"
+pattern = "x"
+message = "never reported"
+"#,
+    );
+    let dispatcher = AnalyzerDispatcher::new_with_config(&config);
+    let files = vec![dir.path().join("app.py")];
+    let mut ids: Vec<String> = dispatcher
+        .run_all(&files, dir.path(), &config)
+        .into_iter()
+        .map(|f| f.id)
+        .collect();
+    ids.sort();
+    assert_eq!(ids, vec!["ACME-001", "CUSTOM-001"]);
+}
@@ -0,0 +1,340 @@
+//! Integration tests for RepositoryAnalyzer and repository-level findings
+
+mod common;
+
+use revet_core::analyzer::repository::RepositoryAnalyzer;
+use revet_core::analyzer::{Analyzer, AnalyzerDispatcher};
+use revet_core::baseline::{self, Baseline, EntryStatus};
+use revet_core::config::RevetConfig;
+use revet_core::diff::{filter_findings_by_diff, DiffFileLines, DiffLineMap};
+use revet_core::finding::{Finding, FindingLocation, Severity};
+use revet_core::policy;
+use revet_core::suppress::{filter_findings_by_inline, filter_findings_by_path_rules};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const FIXTURE: &str = "repository";
+
+fn config(toml: &str) -> RevetConfig {
+    toml::from_str(&format!("[modules]\nrepository = true\n{}", toml)).unwrap()
+}
+
+fn analyze(config: &RevetConfig, root: &Path) -> Vec<Finding> {
+    AnalyzerDispatcher::new_with_config(config).run_all(&[], root, config)
+}
+
+/// Sub-rules of the findings, sorted
+fn rules(findings: &[Finding]) -> Vec<&str> {
+    let mut rules: Vec<_> = findings.iter().filter_map(|f| f.rule.as_deref()).collect();
+    rules.sort();
+    rules
+}
+
+fn repo_with_license() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(tmp.path().join("LICENSE-MIT"), "MIT License").unwrap();
+    tmp
+}
+
+#[test]
+fn test_fixture() {
+    let root = common::fixture_root(FIXTURE);
+    let config = RevetConfig::from_file(&root.join(".revet.toml")).unwrap();
+    let findings = analyze(&config, &root);
+
+    assert_eq!(
+        rules(&findings),
+        vec![
+            "repository:branch_protection",
+            "repository:branch_protection",
+            "repository:license",
+            "repository:no_security",
+        ]
+    );
+    assert!(findings.iter().all(|f| f.id.starts_with("REPO-")));
+    assert!(findings
+        .iter()
+        .all(|f| f.location() == FindingLocation::Repository));
+    assert!(findings.iter().any(|f| f.message.contains("`deploy`")));
+    assert!(findings.iter().any(|f| f.message.contains("`main`")));
+}
+
+#[test]
+fn test_license_variants() {
+    for name in [
+        "LICENSE",
+        "LICENSE.md",
+        "licence.txt",
+        "COPYING",
+        "LICENSE-APACHE",
+    ] {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join(name), "terms").unwrap();
+        assert!(analyze(&config(""), tmp.path()).is_empty(), "{}", name);
+    }
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(tmp.path().join("LICENSES.md"), "terms").unwrap();
+    assert_eq!(
+        rules(&analyze(&config(""), tmp.path())),
+        vec!["repository:license"]
+    );
+}
+
+#[test]
+fn test_stale_baseline() {
+    let tmp = repo_with_license();
+    let findings: Vec<Finding> = (0..5)
+        .map(|i| Finding {
+            id: format!("SEC-{:03}", i + 1),
+            message: format!("secret {}", i),
+            file: tmp.path().join("a.py"),
+            line: i + 1,
+            ..Default::default()
+        })
+        .collect();
+    let mut baseline = Baseline::from_findings(&findings, tmp.path(), None);
+    let toml = "[analyzers.repository]\nstale_baseline_entries = 5\nstale_baseline_days = 90\n";
+
+    // Fresh: not stale yet
+    baseline
+        .save(tmp.path(), &mut common::journal(tmp.path()))
+        .unwrap();
+    assert!(analyze(&config(toml), tmp.path()).is_empty());
+
+    // Created 100 days ago
+    let created: u64 = baseline.created_at.parse().unwrap();
+    baseline.created_at = (created - 100 * 24 * 60 * 60).to_string();
+    baseline
+        .save(tmp.path(), &mut common::journal(tmp.path()))
+        .unwrap();
+    let stale = analyze(&config(toml), tmp.path());
+    assert_eq!(rules(&stale), vec!["repository:stale_baseline"]);
+    assert!(
+        stale[0].message.contains("5 findings"),
+        "{}",
+        stale[0].message
+    );
+    assert!(
+        stale[0].message.contains("100 days"),
+        "{}",
+        stale[0].message
+    );
+
+    // Below the size threshold
+    let toml = "[analyzers.repository]\nstale_baseline_entries = 6\n";
+    assert!(analyze(&config(toml), tmp.path()).is_empty());
+}
+
+#[test]
+fn test_no_security() {
+    let tmp = repo_with_license();
+    let findings = analyze(&config("security = false\n"), tmp.path());
+    assert_eq!(rules(&findings), vec!["repository:no_security"]);
+    assert_eq!(findings[0].severity, Severity::Warning);
+}
+
+#[test]
+fn test_codeowners_locations() {
+    let toml = "[analyzers.repository]\nprotected_branch = \"release\"\n";
+    let tmp = repo_with_license();
+    let findings = analyze(&config(toml), tmp.path());
+    assert_eq!(rules(&findings), vec!["repository:branch_protection"]);
+    assert!(findings[0].message.contains("`release`"));
+
+    for path in ["CODEOWNERS", ".github/CODEOWNERS", "docs/CODEOWNERS"] {
+        let tmp = repo_with_license();
+        let file = tmp.path().join(path);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(file, "* @team").unwrap();
+        assert!(analyze(&config(toml), tmp.path()).is_empty(), "{}", path);
+    }
+}
+
+#[test]
+fn test_required_checks_without_workflows() {
+    let tmp = repo_with_license();
+    let toml = "[analyzers.repository]\nrequired_checks = [\"test\"]\n";
+    let findings = analyze(&config(toml), tmp.path());
+    assert_eq!(rules(&findings), vec!["repository:branch_protection"]);
+    assert!(findings[0].message.contains("`test`"));
+}
+
+#[test]
+fn test_sub_rule_selection() {
+    let tmp = TempDir::new().unwrap();
+    let toml = "security = false\n[analyzers.repository]\ndisable = [\"license\"]\n";
+    assert_eq!(
+        rules(&analyze(&config(toml), tmp.path())),
+        vec!["repository:no_security"]
+    );
+
+    let config: RevetConfig =
+        toml::from_str("[analyzers.repository]\nonly = [\"licence\"]\n").unwrap();
+    let (errors, _) = config.validate();
+    assert!(
+        errors
+            .iter()
+            .any(|e| e.contains("unknown sub-rule \"licence\" for repository")),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_config_validation() {
+    let config: RevetConfig = toml::from_str(
+        "[analyzers.repository]\nstale_baseline_days = 0\nprotected_branch = \" \"\nrequired_checks = [\"\"]\n",
+    )
+    .unwrap();
+    let error = config.validate().0.join("\n");
+    assert!(error.contains("stale_baseline_days"), "{}", error);
+    assert!(error.contains("protected_branch"), "{}", error);
+    assert!(error.contains("required_checks"), "{}", error);
+}
+
+#[test]
+fn test_off_by_default() {
+    let config = RevetConfig::default();
+    assert!(!RepositoryAnalyzer::new().is_enabled(&config));
+    let tmp = TempDir::new().unwrap();
+    assert!(analyze(&config, tmp.path()).is_empty());
+}
+
+// ── Filtering pipeline ───────────────────────────────────────────────────────
+
+const ROOT: &str = "/repo";
+
+fn repository_finding() -> Finding {
+    Finding {
+        id: "REPO-001".to_string(),
+        severity: Severity::Warning,
+        message: "No LICENSE file at the repository root".to_string(),
+        rule: Some("repository:license".to_string()),
+        ..Default::default()
+    }
+}
+
+fn file_finding(file: &str, line: usize) -> Finding {
+    Finding {
+        id: "SEC-001".to_string(),
+        severity: Severity::Error,
+        message: format!("secret in {}", file),
+        file: Path::new(ROOT).join(file),
+        line,
+        ..Default::default()
+    }
+}
+
+fn mixed() -> Vec<Finding> {
+    vec![
+        file_finding("src/a.py", 3),
+        repository_finding(),
+        file_finding("tests/b.py", 7),
+    ]
+}
+
+#[test]
+fn test_location() {
+    assert_eq!(repository_finding().location(), FindingLocation::Repository);
+    assert!(repository_finding().is_repository_level());
+    let located = file_finding("src/a.py", 3);
+    assert!(!located.is_repository_level());
+    assert_eq!(
+        located.location(),
+        FindingLocation::File {
+            path: Path::new("/repo/src/a.py"),
+            line: 3,
+            column: None,
+        }
+    );
+
+    // The JSON shape is unchanged: an empty file and line 0
+    let json = serde_json::to_value(repository_finding()).unwrap();
+    assert_eq!(json["file"], "");
+    assert_eq!(json["line"], 0);
+    let back: Finding = serde_json::from_value(json).unwrap();
+    assert!(back.is_repository_level());
+}
+
+#[test]
+fn test_diff_filter_keeps_repository_findings() {
+    let mut diff_map: DiffLineMap = HashMap::new();
+    diff_map.insert(
+        PathBuf::from("src/a.py"),
+        DiffFileLines::Lines([10].into_iter().collect()),
+    );
+    let (kept, filtered) = filter_findings_by_diff(mixed(), &diff_map, Path::new(ROOT));
+    assert_eq!(filtered, 2);
+    assert_eq!(kept.len(), 1);
+    assert!(kept[0].is_repository_level());
+}
+
+#[test]
+fn test_per_path_rules_skip_repository_findings() {
+    let per_path: HashMap<String, Vec<String>> = [
+        ("**".to_string(), vec!["*".to_string()]),
+        ("*".to_string(), vec!["REPO".to_string()]),
+    ]
+    .into_iter()
+    .collect();
+    let (kept, suppressed) = filter_findings_by_path_rules(mixed(), &per_path, Path::new(ROOT));
+    assert_eq!(suppressed.len(), 2);
+    assert!(suppressed.iter().all(|s| !s.finding.is_repository_level()));
+    assert_eq!(kept.len(), 1);
+    assert!(kept[0].is_repository_level());
+}
+
+#[test]
+fn test_inline_filter_keeps_repository_findings() {
+    let (kept, suppressed) = filter_findings_by_inline(vec![repository_finding()]);
+    assert_eq!(kept.len(), 1);
+    assert!(suppressed.is_empty());
+}
+
+#[test]
+fn test_baseline_matches_repository_findings() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path();
+    let base = Baseline::from_findings(&[repository_finding()], root, None);
+    assert_eq!(base.entries[0].file, "");
+
+    let current = vec![
+        repository_finding(),
+        Finding {
+            message: "A different repository finding".to_string(),
+            ..repository_finding()
+        },
+    ];
+    let (new, suppressed) = baseline::filter_findings(current.clone(), &base, root);
+    assert_eq!(suppressed.len(), 1);
+    assert_eq!(new.len(), 1);
+    assert!(new[0].message.starts_with("A different"));
+
+    // An entry with nothing to match is unmatched, not a deleted file
+    let statuses = baseline::entry_statuses(&base, &[], root);
+    assert_eq!(statuses[0].status, EntryStatus::Unmatched);
+    let statuses = baseline::entry_statuses(&base, &current, root);
+    assert_eq!(statuses[0].status, EntryStatus::Matched);
+}
+
+#[test]
+fn test_policy_sees_repository_findings() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[[policy]]
+name = "repo-hygiene"
+filter = "module == repository"
+aggregate = "exists"
+
+[[policy]]
+name = "src-only"
+filter = "path ~ \"src/**\""
+"#,
+    )
+    .unwrap();
+    let policies = policy::compile(&config.policies).unwrap();
+    let report = policy::evaluate(&policies, &mixed(), &[], Path::new(ROOT));
+    assert_eq!(report.get("repo-hygiene").unwrap().count, 1);
+    assert_eq!(report.get("src-only").unwrap().count, 1);
+}
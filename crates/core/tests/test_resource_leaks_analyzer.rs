@@ -0,0 +1,413 @@
+use revet_core::analyzer::resource_leaks::ResourceLeaksAnalyzer;
+use revet_core::analyzer::Analyzer;
+use revet_core::config::RevetConfig;
+use revet_core::{AnalyzerDispatcher, Finding, Severity};
+use std::path::Path;
+
+fn run(file: &str, source: &str) -> Vec<Finding> {
+    ResourceLeaksAnalyzer::new().analyze_source(Path::new(file), source, Path::new("/repo"))
+}
+
+fn java(source: &str) -> Vec<Finding> {
+    run("OrderService.java", source)
+}
+
+fn kotlin(source: &str) -> Vec<Finding> {
+    run("OrderService.kt", source)
+}
+
+fn lines(findings: &[Finding]) -> Vec<usize> {
+    findings.iter().map(|f| f.line).collect()
+}
+
+// ── Java: resources ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_java_unclosed_connection() {
+    let findings = java(
+        r#"
+class OrderService {
+    int count() throws SQLException {
+        Connection conn = dataSource.getConnection();
+        Statement st = conn.createStatement();
+        return st.executeQuery("select 1").getInt(1);
+    }
+}
+"#,
+    );
+    assert_eq!(lines(&findings), vec![4, 5], "{:?}", findings);
+    let f = &findings[0];
+    assert_eq!(f.severity, Severity::Warning);
+    assert_eq!(f.message, "`Connection` is not closed on all paths");
+    assert_eq!(
+        f.suggestion.as_deref(),
+        Some(
+            "Use try-with-resources: `try (Connection conn = dataSource.getConnection()) { ... }`"
+        )
+    );
+}
+
+#[test]
+fn test_java_stream_used_in_place_or_passed_to_a_method() {
+    let findings = java(
+        r#"
+class Loader {
+    void load(File f, Properties props) throws IOException {
+        int first = new FileInputStream(f).read();
+        props.load(new FileInputStream(f));
+        InputStream in = Files.newInputStream(f.toPath());
+        props.load(in);
+    }
+}
+"#,
+    );
+    assert_eq!(lines(&findings), vec![4, 5, 6], "{:?}", findings);
+}
+
+#[test]
+fn test_java_close_outside_finally_is_flagged() {
+    let findings = java(
+        r#"
+class Loader {
+    void load(File f) throws IOException {
+        FileReader reader = new FileReader(f);
+        reader.read();
+        reader.close();
+    }
+}
+"#,
+    );
+    assert_eq!(lines(&findings), vec![4]);
+}
+
+#[test]
+fn test_java_correct_patterns() {
+    let findings = java(
+        r#"
+class OrderService {
+    private final Connection shared = openShared();
+    private InputStream cached;
+
+    int count() throws SQLException {
+        try (Connection conn = dataSource.getConnection();
+             PreparedStatement st = conn.prepareStatement("select 1")) {
+            return st.executeQuery().getInt(1);
+        }
+    }
+
+    Connection open() throws SQLException {
+        return dataSource.getConnection();
+    }
+
+    Connection openLocal() throws SQLException {
+        Connection conn = dataSource.getConnection();
+        conn.setAutoCommit(false);
+        return conn;
+    }
+
+    void cache(File f) throws IOException {
+        this.cached = new FileInputStream(f);
+        InputStream in = new FileInputStream(f);
+        cached = in;
+    }
+
+    String firstLine(File f) throws IOException {
+        InputStream in = new FileInputStream(f);
+        try (BufferedReader r = new BufferedReader(new InputStreamReader(in))) {
+            return r.readLine();
+        }
+    }
+
+    void legacy() throws SQLException {
+        Connection conn = null;
+        try {
+            conn = dataSource.getConnection();
+            conn.commit();
+        } finally {
+            if (conn != null) {
+                conn.close();
+            }
+        }
+    }
+
+    void java9(File f) throws IOException {
+        FileInputStream in = new FileInputStream(f);
+        try (in) {
+            in.read();
+        }
+    }
+
+    void borrowed(Socket socket) throws IOException {
+        InputStream in = socket.getInputStream();
+        in.read();
+    }
+
+    Runnable task() {
+        return new Runnable() {
+            public void run() {}
+        };
+    }
+}
+"#,
+    );
+    assert!(findings.is_empty(), "{:?}", findings);
+}
+
+#[test]
+fn test_java_declared_type_and_configured_types() {
+    let source = r#"
+class Pool {
+    void use() {
+        Connection conn = pool.borrow();
+        BlobHandle blob = new BlobHandle(id);
+        conn.commit();
+    }
+}
+"#;
+    assert_eq!(lines(&java(source)), vec![4]);
+
+    let analyzer = ResourceLeaksAnalyzer::with_types(&["com.acme.BlobHandle".to_string()]);
+    let findings = analyzer.analyze_source(Path::new("Pool.java"), source, Path::new("/repo"));
+    assert_eq!(lines(&findings), vec![4, 5]);
+}
+
+// ── Java: transactions ───────────────────────────────────────────────────────
+
+#[test]
+fn test_java_multiple_writes_without_transaction() {
+    let findings = java(
+        r#"
+@Service
+class OrderService {
+    public void place(Order order) {
+        orderRepository.save(order);
+        this.inventoryDao.update(order.items());
+    }
+
+    public void single(Order order) {
+        orderRepository.save(order);
+    }
+
+    public void reads(Order order) {
+        orderRepository.findById(order.id());
+        orderRepository.findAll();
+    }
+
+    public void notRepositories(Order order) {
+        cache.save(order);
+        cache.delete(order);
+    }
+}
+"#,
+    );
+    assert_eq!(lines(&findings), vec![4], "{:?}", findings);
+    let f = &findings[0];
+    assert_eq!(
+        f.message,
+        "`place` makes 2 repository writes without a transaction (orderRepository.save, inventoryDao.update)"
+    );
+    assert!(f.suggestion.as_deref().unwrap().contains("@Transactional"));
+}
+
+#[test]
+fn test_java_transactional_method_class_or_callback() {
+    let findings = java(
+        r#"
+class A {
+    @Transactional
+    public void place(Order order) {
+        orderRepository.save(order);
+        orderRepository.delete(old);
+    }
+
+    @org.springframework.transaction.annotation.Transactional(readOnly = false)
+    public void qualified(Order order) {
+        orderRepository.save(order);
+        orderRepository.delete(old);
+    }
+
+    public void template(Order order) {
+        transactionTemplate.execute(status -> {
+            orderRepository.save(order);
+            orderRepository.delete(old);
+            return null;
+        });
+    }
+}
+
+@Transactional
+class B {
+    public void place(Order order) {
+        orderRepository.save(order);
+        entityManager.persist(audit);
+    }
+}
+"#,
+    );
+    assert!(findings.is_empty(), "{:?}", findings);
+}
+
+// ── Kotlin ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_kotlin_unclosed_resources() {
+    let findings = kotlin(
+        r#"
+class Loader(private val db: SQLiteDatabase) {
+    fun load(file: File): Int {
+        val stream = FileInputStream(file)
+        val cursor: Cursor = db.query("orders")
+        FileInputStream(file).readBytes()
+        return stream.read() + cursor.count
+    }
+}
+"#,
+    );
+    assert_eq!(lines(&findings), vec![4, 5, 6], "{:?}", findings);
+    assert_eq!(
+        findings[0].suggestion.as_deref(),
+        Some("Use a `use` block: `FileInputStream(file).use { stream -> ... }`")
+    );
+}
+
+#[test]
+fn test_kotlin_correct_patterns() {
+    let findings = kotlin(
+        r#"
+class Loader(private val dataSource: DataSource) {
+    private val log = FileOutputStream("app.log")
+    private var current: InputStream? = null
+
+    fun read(file: File): Int = FileInputStream(file).use { it.read() }
+
+    fun lines(file: File): List<String> =
+        FileInputStream(file).bufferedReader().use { it.readLines() }
+
+    fun open(file: File): InputStream = FileInputStream(file)
+
+    fun openLocal(file: File): InputStream {
+        val stream = FileInputStream(file)
+        return stream
+    }
+
+    fun keep(file: File) {
+        val stream = FileInputStream(file)
+        this.current = stream
+    }
+
+    fun count(): Int {
+        val conn = dataSource.getConnection()
+        return conn.use { it.createStatement().use { st -> st.executeQuery("select 1").getInt(1) } }
+    }
+
+    fun legacy(file: File) {
+        val stream = FileInputStream(file)
+        try {
+            stream.read()
+        } finally {
+            stream.close()
+        }
+    }
+}
+"#,
+    );
+    assert!(findings.is_empty(), "{:?}", findings);
+}
+
+#[test]
+fn test_kotlin_transactions() {
+    let findings = kotlin(
+        r#"
+class OrderService(private val orderRepository: OrderRepository) {
+    fun place(order: Order) {
+        orderRepository.save(order)
+        auditDao.insert(Audit(order))
+    }
+
+    @Transactional
+    fun annotated(order: Order) {
+        orderRepository.save(order)
+        auditDao.insert(Audit(order))
+    }
+
+    fun exposed(order: Order) {
+        transaction {
+            orderRepository.save(order)
+            auditDao.insert(Audit(order))
+        }
+    }
+}
+
+@Dao
+interface OrderDao {
+    @Transaction
+    fun replace(order: Order) {
+        orderDao.delete(order)
+        orderDao.insert(order)
+    }
+}
+"#,
+    );
+    assert_eq!(lines(&findings), vec![3], "{:?}", findings);
+    assert!(findings[0]
+        .suggestion
+        .as_deref()
+        .unwrap()
+        .contains("@Transaction"));
+}
+
+// ── Wiring ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_ignores_other_languages() {
+    assert!(run("loader.py", "stream = open('x')\n").is_empty());
+}
+
+#[test]
+fn test_disabled_by_default_and_configurable() {
+    let analyzer = ResourceLeaksAnalyzer::new();
+    assert!(!analyzer.is_enabled(&RevetConfig::default()));
+
+    let config: RevetConfig = toml::from_str(
+        r#"
+[modules]
+resource_leaks = true
+
+[resource_leaks]
+closeable_types = ["BlobHandle"]
+"#,
+    )
+    .unwrap();
+    assert!(analyzer.is_enabled(&config));
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("Pool.java");
+    std::fs::write(
+        &file,
+        "class Pool { void f() { BlobHandle b = new BlobHandle(1); } }\n",
+    )
+    .unwrap();
+    let findings =
+        AnalyzerDispatcher::new_with_config(&config).run_all(&[file], dir.path(), &config);
+    assert_eq!(findings.len(), 1, "{:?}", findings);
+    assert_eq!(findings[0].id, "RES-001");
+}
+
+#[test]
+fn test_validation_rejects_bad_type_names() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[resource_leaks]
+closeable_types = ["", "Blob Handle"]
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    assert_eq!(
+        errors
+            .iter()
+            .filter(|e| e.contains("[resource_leaks]"))
+            .count(),
+        2
+    );
+}
@@ -0,0 +1,221 @@
+//! Tests for the review effort estimate: bands, factors, complexity-weighted
+//! lines and suggested reviewers
+
+use revet_core::analyzer::complexity::FunctionComplexity;
+use revet_core::analyzer::ownership::codeowners::CodeOwners;
+use revet_core::authorship::{Authorship, FileAuthors};
+use revet_core::diff::estimate::{
+    suggest_reviewers, weighted_lines, Band, EstimateInputs, ReviewEstimate,
+};
+use revet_core::ReviewEstimateConfig;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+// ── Bands ────────────────────────────────────────────────────────
+
+#[test]
+fn test_band_boundaries() {
+    let bands = [15.0, 60.0, 180.0];
+    assert_eq!(Band::of(0.0, &bands), Band::S);
+    assert_eq!(Band::of(14.99, &bands), Band::S);
+    assert_eq!(Band::of(15.0, &bands), Band::M);
+    assert_eq!(Band::of(59.99, &bands), Band::M);
+    assert_eq!(Band::of(60.0, &bands), Band::L);
+    assert_eq!(Band::of(179.99, &bands), Band::L);
+    assert_eq!(Band::of(180.0, &bands), Band::XL);
+    assert_eq!(Band::of(10_000.0, &bands), Band::XL);
+}
+
+// ── Factors ──────────────────────────────────────────────────────
+
+#[test]
+fn test_compute_lists_every_factor() {
+    let inputs = EstimateInputs {
+        changed_lines: 40,
+        weighted_lines: 50.0,
+        files: 3,
+        projects: 2,
+        risk: 40,
+        errors: 1,
+        warnings: 2,
+        info: 4,
+    };
+    let estimate = ReviewEstimate::compute(&inputs, &ReviewEstimateConfig::default());
+
+    let factors: Vec<(&str, f64, f64)> = estimate
+        .factors
+        .iter()
+        .map(|f| (f.name.as_str(), f.value, f.minutes))
+        .collect();
+    assert_eq!(
+        factors,
+        [
+            ("lines", 50.0, 10.0),
+            ("files", 3.0, 6.0),
+            ("projects", 2.0, 10.0),
+            ("risk", 40.0, 12.0),
+            ("errors", 1.0, 5.0),
+            ("warnings", 2.0, 4.0),
+            ("info", 4.0, 2.0),
+        ]
+    );
+    assert_eq!(estimate.minutes, 49.0);
+    assert_eq!(estimate.band, Band::M);
+    assert_eq!(estimate.changed_lines, 40);
+    assert_eq!(estimate.bands, [15.0, 60.0, 180.0]);
+}
+
+#[test]
+fn test_compute_is_deterministic() {
+    let inputs = EstimateInputs {
+        weighted_lines: 12.35,
+        files: 1,
+        ..Default::default()
+    };
+    let config = ReviewEstimateConfig::default();
+    assert_eq!(
+        ReviewEstimate::compute(&inputs, &config),
+        ReviewEstimate::compute(&inputs, &config)
+    );
+}
+
+// ── Weighted lines ───────────────────────────────────────────────
+
+#[test]
+fn test_lines_weigh_by_innermost_function() {
+    let root = Path::new("/repo");
+    let function = |line, end_line, complexity| FunctionComplexity {
+        file: root.join("a.py"),
+        line,
+        end_line,
+        complexity,
+    };
+    let functions = [function(1, 20, 5), function(5, 8, 1)];
+    let files = BTreeMap::from([(PathBuf::from("a.py"), vec![3, 6, 30])]);
+
+    // Line 3 in the outer function (complexity 5), line 6 in the nested
+    // one (1), line 30 outside both
+    assert_eq!(
+        weighted_lines(root, &files, &functions, 0.5),
+        3.0 + 1.0 + 1.0
+    );
+    assert_eq!(weighted_lines(root, &files, &functions, 0.0), 3.0);
+}
+
+// ── Reviewers ────────────────────────────────────────────────────
+
+fn authorship(files: &[(&str, &[(&str, usize)])], active: &[&str]) -> Authorship {
+    Authorship {
+        active: active.iter().map(|a| a.to_string()).collect(),
+        files: files
+            .iter()
+            .map(|(file, lines)| {
+                let lines = lines.iter().map(|(a, n)| (a.to_string(), *n)).collect();
+                (
+                    file.to_string(),
+                    FileAuthors {
+                        lines,
+                        last_change: 0,
+                    },
+                )
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn files(list: &[&str]) -> Vec<String> {
+    list.iter().map(|f| f.to_string()).collect()
+}
+
+#[test]
+fn test_reviewers_are_owners_who_changed_the_files() {
+    let owners = CodeOwners::parse("/api/ @alice @dev\n/web/ @acme/web\n");
+    let authorship = authorship(
+        &[
+            (
+                "api/a.py",
+                &[("alice@example.com", 10), ("dev@example.com", 2)],
+            ),
+            ("web/b.ts", &[("bob@example.com", 30)]),
+        ],
+        &["alice@example.com", "bob@example.com", "dev@example.com"],
+    );
+    let reviewers = suggest_reviewers(
+        &files(&["api/a.py", "web/b.ts"]),
+        &owners,
+        &authorship,
+        Some("dev@example.com"),
+        3,
+    );
+
+    // bob only matches a team; dev wrote the change
+    let names: Vec<&str> = reviewers.iter().map(|r| r.author.as_str()).collect();
+    assert_eq!(names, ["alice@example.com"]);
+    assert!(reviewers[0].owner);
+    assert_eq!((reviewers[0].files, reviewers[0].lines), (1, 10));
+}
+
+#[test]
+fn test_reviewers_without_owners_are_recent_authors() {
+    let authorship = authorship(
+        &[
+            (
+                "a.py",
+                &[("alice@example.com", 5), ("carol@example.com", 50)],
+            ),
+            (
+                "b.py",
+                &[("alice@example.com", 5), ("gone@example.com", 80)],
+            ),
+        ],
+        &["alice@example.com", "carol@example.com"],
+    );
+    let reviewers = suggest_reviewers(
+        &files(&["a.py", "b.py"]),
+        &CodeOwners::default(),
+        &authorship,
+        None,
+        3,
+    );
+
+    // Most files first, then most lines; authors outside the window drop
+    let names: Vec<&str> = reviewers.iter().map(|r| r.author.as_str()).collect();
+    assert_eq!(names, ["alice@example.com", "carol@example.com"]);
+    assert!(reviewers.iter().all(|r| !r.owner));
+
+    let one = suggest_reviewers(
+        &files(&["a.py", "b.py"]),
+        &CodeOwners::default(),
+        &authorship,
+        None,
+        1,
+    );
+    assert_eq!(one.len(), 1);
+}
+
+#[test]
+fn test_owner_handles_match_noreply_and_emails() {
+    let owners = CodeOwners::parse("* @Alice carol@example.com\n");
+    let authorship = authorship(
+        &[(
+            "a.py",
+            &[
+                ("12345+alice@users.noreply.github.com", 1),
+                ("carol@example.com", 1),
+                ("dave@example.com", 1),
+            ],
+        )],
+        &[
+            "12345+alice@users.noreply.github.com",
+            "carol@example.com",
+            "dave@example.com",
+        ],
+    );
+    let reviewers = suggest_reviewers(&files(&["a.py"]), &owners, &authorship, None, 5);
+    let names: Vec<&str> = reviewers.iter().map(|r| r.author.as_str()).collect();
+    assert_eq!(
+        names,
+        ["12345+alice@users.noreply.github.com", "carol@example.com"]
+    );
+}
@@ -0,0 +1,211 @@
+//! Tests for fan-in weighted risk scoring
+
+use revet_core::config::RiskConfig;
+use revet_core::diff::risk::{decayed_reach, score};
+use revet_core::graph::{CodeGraph, Edge, EdgeKind, Node, NodeData, NodeKind, Parameter};
+use revet_core::{ImpactAnalysis, RiskLevel, RiskReport};
+use std::path::{Path, PathBuf};
+
+const ROOT: &str = "/repo";
+
+fn function(graph: &mut CodeGraph, name: &str, file: &str, params: usize) -> revet_core::NodeId {
+    graph.add_node(Node::new(
+        NodeKind::Function,
+        name.to_string(),
+        PathBuf::from(ROOT).join(file),
+        1,
+        NodeData::Function {
+            parameters: (0..params)
+                .map(|i| Parameter {
+                    name: format!("p{}", i),
+                    param_type: None,
+                    default_value: None,
+                })
+                .collect(),
+            return_type: None,
+        },
+    ))
+}
+
+/// `hot` has 15 direct callers (one of which has its own caller) plus one
+/// class referencing it; `cold` has a single caller under `src/billing/`.
+/// `changed` adds a parameter to both so they show up in the diff.
+fn build(changed: bool) -> CodeGraph {
+    let params = usize::from(changed);
+    let mut graph = CodeGraph::new(PathBuf::from(ROOT));
+
+    let hot = function(&mut graph, "hot", "src/core/hot.py", params);
+    let mut first_caller = None;
+    for i in 0..15 {
+        let caller = function(
+            &mut graph,
+            &format!("caller_{}", i),
+            "src/app/callers.py",
+            0,
+        );
+        graph.add_edge(caller, hot, Edge::new(EdgeKind::Calls));
+        first_caller.get_or_insert(caller);
+    }
+    let outer = function(&mut graph, "outer", "src/app/outer.py", 0);
+    graph.add_edge(outer, first_caller.unwrap(), Edge::new(EdgeKind::Calls));
+
+    let registry = graph.add_node(Node::new(
+        NodeKind::Class,
+        "Registry".to_string(),
+        PathBuf::from(ROOT).join("src/app/registry.py"),
+        1,
+        NodeData::Class {
+            base_classes: vec![],
+            methods: vec![],
+            fields: vec![],
+        },
+    ));
+    graph.add_edge(registry, hot, Edge::new(EdgeKind::References));
+
+    let cold = function(&mut graph, "cold", "src/util/cold.py", params);
+    let pay = function(&mut graph, "pay", "src/billing/pay.py", 0);
+    graph.add_edge(pay, cold, Edge::new(EdgeKind::Calls));
+
+    graph
+}
+
+fn risk(config: &RiskConfig) -> RiskReport {
+    let analysis = ImpactAnalysis::new(build(false), build(true));
+    let report = analysis.analyze_impact();
+    RiskReport::compute(&report, analysis.new_graph(), Path::new(ROOT), config)
+}
+
+fn critical_config() -> RiskConfig {
+    RiskConfig {
+        critical_paths: vec!["src/billing/**".to_string()],
+        ..RiskConfig::default()
+    }
+}
+
+// ── Formula ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_score_formula_pinned() {
+    assert_eq!(score(0, 0.0, false).total, 0);
+    // 50·log2(2)/8 + 30·log2(2)/7 = 6.25 + 4.29
+    let one = score(1, 1.0, false);
+    assert_eq!((one.fan_in, one.reach, one.total), (6.25, 4.29, 11));
+    // 50·4/8 + 30·4/7 = 25 + 17.14
+    assert_eq!(score(15, 15.0, false).total, 42);
+    assert_eq!(score(1, 1.0, true).total, 31);
+    // Saturation
+    assert_eq!(score(255, 127.0, false).total, 80);
+    assert_eq!(score(214, 300.0, false).total, 78);
+    assert_eq!(score(100_000, 1e9, true).total, 100);
+}
+
+#[test]
+fn test_decayed_reach_halves_per_level() {
+    assert_eq!(decayed_reach(&[]), 0.0);
+    assert_eq!(decayed_reach(&[1, 1, 2, 3]), 2.75);
+}
+
+// ── Synthetic graph ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_symbol_scores_on_synthetic_graph() {
+    let report = risk(&critical_config());
+    assert_eq!(report.symbols.len(), 2);
+
+    let hot = &report.symbols[0];
+    assert_eq!(hot.name, "hot");
+    // 15 callers + 1 referencing class
+    assert_eq!(hot.direct_fan_in, 16);
+    // 15 direct callers (depth 1) + outer (depth 2)
+    assert_eq!(hot.transitive_dependents, 16);
+    assert_eq!(hot.decayed_reach, 15.5);
+    assert_eq!(hot.critical_dependents, 0);
+    assert_eq!(hot.score, 43);
+    assert_eq!(hot.level, RiskLevel::Medium);
+
+    let cold = &report.symbols[1];
+    assert_eq!(cold.name, "cold");
+    assert_eq!(cold.direct_fan_in, 1);
+    assert_eq!(cold.critical_dependents, 1);
+    assert_eq!(cold.breakdown.critical, 20.0);
+    assert_eq!(cold.score, 31);
+}
+
+#[test]
+fn test_file_and_pr_aggregates() {
+    let report = risk(&critical_config());
+    let files: Vec<(String, u8)> = report
+        .files
+        .iter()
+        .map(|f| (f.file.display().to_string(), f.score))
+        .collect();
+    assert_eq!(
+        files,
+        vec![
+            ("src/core/hot.py".to_string(), 43),
+            ("src/util/cold.py".to_string(), 31)
+        ]
+    );
+    assert_eq!(report.score, 43);
+    assert_eq!(report.level, RiskLevel::Medium);
+    assert_eq!(
+        report.headline(),
+        "Risk: 43 (medium) — `hot()` has 16 direct callers"
+    );
+}
+
+#[test]
+fn test_without_critical_paths() {
+    let report = risk(&RiskConfig::default());
+    let cold = report.symbols.iter().find(|s| s.name == "cold").unwrap();
+    assert_eq!(cold.critical_dependents, 0);
+    assert_eq!(cold.score, 11);
+}
+
+#[test]
+fn test_thresholds_from_config() {
+    let config = RiskConfig {
+        medium: 20,
+        high: 40,
+        critical_paths: vec![],
+    };
+    let report = risk(&config);
+    assert_eq!(report.level, RiskLevel::High);
+    assert_eq!(report.symbols[1].level, RiskLevel::Low);
+}
+
+#[test]
+fn test_scoring_is_deterministic() {
+    let a = serde_json::to_string(&risk(&critical_config())).unwrap();
+    let b = serde_json::to_string(&risk(&critical_config())).unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_empty_report() {
+    let analysis = ImpactAnalysis::new(build(false), build(false));
+    let report = RiskReport::compute(
+        &analysis.analyze_impact(),
+        analysis.new_graph(),
+        Path::new(ROOT),
+        &RiskConfig::default(),
+    );
+    assert!(report.symbols.is_empty());
+    assert_eq!(report.headline(), "Risk: 0 (low)");
+}
+
+#[test]
+fn test_risk_config_validation() {
+    let config: revet_core::RevetConfig = toml::from_str(
+        r#"
+[risk]
+medium = 70
+high = 50
+critical_paths = ["src/[billing"]
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    assert!(errors.iter().any(|e| e.contains("medium < high")));
+    assert!(errors.iter().any(|e| e.contains("invalid glob")));
+}
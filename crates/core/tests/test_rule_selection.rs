@@ -0,0 +1,314 @@
+//! Tests for sub-rule selection (`[analyzers.<module>] only / disable`)
+
+use revet_core::analyzer::rules;
+use revet_core::config::RuleSelection;
+use revet_core::{AnalyzerDispatcher, Finding, RevetConfig};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// One line per sub-rule, each triggering only that sub-rule
+const ERROR_HANDLING: &[(&str, &str, &str)] = &[
+    ("empty_catch", "app.js", "try { run(); } catch (e) {}"),
+    ("bare_except", "app.py", "except:"),
+    ("unwrap", "lib.rs", "let value = input.unwrap();"),
+    ("panic", "lib.rs", "panic!(\"unreachable state\");"),
+    (
+        "expect_message",
+        "lib.rs",
+        "let v = input.expect(\"failed\");",
+    ),
+    ("swallowed", "app.js", "} catch (err) { console.log(err); }"),
+    ("broad_except", "app.py", "except Exception as e:"),
+    ("empty_promise_catch", "app.js", "load().catch(() => {});"),
+    ("discarded_error", "main.go", "_ = err"),
+];
+
+const ASYNC_PATTERNS: &[(&str, &str, &str)] = &[
+    (
+        "async_executor",
+        "jobs.js",
+        "const p = new Promise(async (resolve) => resolve(1));",
+    ),
+    (
+        "foreach_await",
+        "jobs.js",
+        "items.forEach(async (i) => save(i));",
+    ),
+    (
+        "unhandled_then",
+        "jobs.js",
+        "fetch(url).then((r) => r.json());",
+    ),
+    (
+        "map_without_all",
+        "jobs.js",
+        "const r = items.map(async (i) => load(i));",
+    ),
+    (
+        "async_timer",
+        "jobs.js",
+        "setTimeout(async () => tick(), 10);",
+    ),
+    ("unawaited", "jobs.py", "    asyncio.sleep(1)"),
+    ("swallowed_catch", "jobs.js", "load().catch((e) => {});"),
+    ("return_await", "jobs.js", "  return await load();"),
+];
+
+/// Write each case's line into its file and return the files
+fn write_cases(root: &Path, cases: &[(&str, &str, &str)]) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for (_, file, line) in cases {
+        let path = root.join(file);
+        let mut content = std::fs::read_to_string(&path).unwrap_or_default();
+        content.push_str(line);
+        content.push('\n');
+        std::fs::write(&path, content).unwrap();
+        if !files.contains(&path) {
+            files.push(path);
+        }
+    }
+    files
+}
+
+fn config_for(module: &str, selection: RuleSelection) -> RevetConfig {
+    let mut config = RevetConfig::default();
+    config.modules.error_handling = true;
+    config.modules.async_patterns = true;
+    config.analyzers.rules.insert(module.to_string(), selection);
+    config
+}
+
+/// Sub-rules of `module` reported for `files`
+fn reported(files: &[PathBuf], root: &Path, module: &str, config: &RevetConfig) -> Vec<String> {
+    let findings = AnalyzerDispatcher::new_with_config(config).run_all(files, root, config);
+    rules_of(&findings, module)
+}
+
+fn rules_of(findings: &[Finding], module: &str) -> Vec<String> {
+    let prefix = format!("{}:", module);
+    let rules: BTreeSet<String> = findings
+        .iter()
+        .filter_map(|f| f.rule.as_deref()?.strip_prefix(&prefix))
+        .map(String::from)
+        .collect();
+    rules.into_iter().collect()
+}
+
+fn sorted<'a>(rules: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let set: BTreeSet<String> = rules.into_iter().map(String::from).collect();
+    set.into_iter().collect()
+}
+
+/// Toggle every sub-rule of `module` on its own, both ways
+fn assert_matrix(module: &str, cases: &[(&str, &str, &str)]) {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().to_path_buf();
+    let files = write_cases(&root, cases);
+    let all = sorted(cases.iter().map(|(rule, _, _)| *rule));
+
+    let config = config_for(module, RuleSelection::default());
+    assert_eq!(reported(&files, &root, module, &config), all);
+
+    for (rule, _, _) in cases {
+        let only = config_for(
+            module,
+            RuleSelection {
+                only: vec![rule.to_string()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            reported(&files, &root, module, &only),
+            vec![rule.to_string()],
+            "only = [{rule:?}]"
+        );
+
+        let disabled = config_for(
+            module,
+            RuleSelection {
+                only: Vec::new(),
+                disable: vec![rule.to_string()],
+                ..Default::default()
+            },
+        );
+        let expected = sorted(cases.iter().map(|(r, _, _)| *r).filter(|r| r != rule));
+        assert_eq!(
+            reported(&files, &root, module, &disabled),
+            expected,
+            "disable = [{rule:?}]"
+        );
+    }
+}
+
+// ── Matrix ───────────────────────────────────────────────────────
+
+#[test]
+fn test_error_handling_matrix() {
+    assert_matrix("error_handling", ERROR_HANDLING);
+}
+
+#[test]
+fn test_async_patterns_matrix() {
+    assert_matrix("async_patterns", ASYNC_PATTERNS);
+}
+
+#[test]
+fn test_matrix_covers_every_sub_rule() {
+    let catalog = rules::catalog();
+    for (module, cases) in [
+        ("error_handling", ERROR_HANDLING),
+        ("async_patterns", ASYNC_PATTERNS),
+    ] {
+        assert_eq!(
+            sorted(catalog[module].iter().copied()),
+            sorted(cases.iter().map(|(rule, _, _)| *rule)),
+            "{module}"
+        );
+    }
+}
+
+// ── Selection semantics ──────────────────────────────────────────
+
+#[test]
+fn test_deselected_rule_lets_a_later_pattern_report_the_line() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().to_path_buf();
+    let files = write_cases(&root, &[("", "app.py", "except: pass")]);
+
+    let config = config_for("error_handling", RuleSelection::default());
+    assert_eq!(
+        reported(&files, &root, "error_handling", &config),
+        vec!["empty_catch"]
+    );
+
+    let config = config_for(
+        "error_handling",
+        RuleSelection {
+            only: Vec::new(),
+            disable: vec!["empty_catch".to_string()],
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        reported(&files, &root, "error_handling", &config),
+        vec!["bare_except"]
+    );
+}
+
+#[test]
+fn test_plain_dispatcher_filters_by_selection() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().to_path_buf();
+    let files = write_cases(&root, ERROR_HANDLING);
+    let config = config_for(
+        "error_handling",
+        RuleSelection {
+            only: vec!["unwrap".to_string(), "panic".to_string()],
+            disable: vec!["panic".to_string()],
+            ..Default::default()
+        },
+    );
+
+    let findings = AnalyzerDispatcher::new().run_all(&files, &root, &config);
+    assert_eq!(rules_of(&findings, "error_handling"), vec!["unwrap"]);
+    let ids: Vec<&str> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("ERR"))
+        .map(|f| f.id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["ERR-001"], "renumbered after selection");
+}
+
+#[test]
+fn test_module_without_selected_rules_does_not_run() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().to_path_buf();
+    let files = write_cases(&root, &[("", "app.py", "password = \"hunter2hunter2\"")]);
+    let mut config = config_for(
+        "security",
+        RuleSelection {
+            only: vec!["sql_injection".to_string()],
+            ..Default::default()
+        },
+    );
+    config.modules.ml = false;
+
+    let (_, timings) =
+        AnalyzerDispatcher::new_with_config(&config).run_all_parallel_timed(&files, &root, &config);
+    let prefixes: Vec<&str> = timings.iter().map(|t| t.prefix.as_str()).collect();
+    assert!(prefixes.contains(&"SQL"), "{prefixes:?}");
+    assert!(!prefixes.contains(&"SEC"), "{prefixes:?}");
+}
+
+#[test]
+fn test_findings_carry_qualified_rule() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().to_path_buf();
+    let files = write_cases(&root, &[("", "lib.rs", "let value = input.unwrap();")]);
+    let config = config_for("error_handling", RuleSelection::default());
+
+    let findings = AnalyzerDispatcher::new_with_config(&config).run_all(&files, &root, &config);
+    let json = serde_json::to_value(&findings[0]).unwrap();
+    assert_eq!(json["rule"], "error_handling:unwrap");
+}
+
+// ── Config and validation ────────────────────────────────────────
+
+#[test]
+fn test_parse_analyzers_tables() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[analyzers.error_handling]
+only = ["swallowed"]
+
+[analyzers.async_patterns]
+disable = ["return_await"]
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.rule_selection("error_handling").only,
+        vec!["swallowed"]
+    );
+    assert!(!config
+        .rule_selection("async_patterns")
+        .allows("return_await"));
+    assert!(config.rule_selection("react").allows("missing_key"));
+    assert!(config.analyzers.generated.is_empty());
+}
+
+#[test]
+fn test_unknown_sub_rule_lists_valid_ones() {
+    let err = rules::check("async_patterns", ["unawaitd"]).unwrap_err();
+    assert!(err.starts_with("unknown sub-rule \"unawaitd\" for async_patterns (valid: "));
+    assert!(err.contains("unawaited"));
+
+    let err = rules::check("magic_numbers", ["literal"]).unwrap_err();
+    assert!(err.contains("magic_numbers has no sub-rules"), "{err}");
+
+    assert!(rules::check("error_handling", ["swallowed", "unwrap"]).is_ok());
+}
+
+#[test]
+fn test_validate_reports_unknown_module_and_sub_rule() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[analyzers.error_handling]
+disable = ["unwarp"]
+
+[analyzers.eror_handling]
+only = ["unwrap"]
+"#,
+    )
+    .unwrap();
+
+    let (errors, _) = config.validate();
+    assert!(errors
+        .iter()
+        .any(|e| e.starts_with("[analyzers.error_handling] unknown sub-rule \"unwarp\"")));
+    assert!(errors
+        .iter()
+        .any(|e| e.starts_with("[analyzers.eror_handling] unknown module")));
+}
@@ -0,0 +1,527 @@
+//! Tests for lockfile parsing and SBOM generation (`revet sbom`)
+
+use jsonschema::{Draft, JSONSchema};
+use revet_core::lockfile::{self, advisory::load_advisories, Ecosystem, LockedPackage};
+use revet_core::{RevetConfig, Sbom, SbomFormat};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+    let path = dir.join(name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).unwrap();
+    }
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+const PACKAGE_JSON: &str = r#"{ "name": "web", "dependencies": { "lodash": "^4.17.0" } }"#;
+
+const PACKAGE_LOCK_V3: &str = r#"{
+  "name": "web",
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "name": "web", "dependencies": { "lodash": "^4.17.0" } },
+    "node_modules/lodash": { "version": "4.17.20" },
+    "node_modules/@babel/core": { "version": "7.24.0", "dev": true },
+    "node_modules/@babel/core/node_modules/semver": { "version": "6.3.1" },
+    "node_modules/local-lib": { "resolved": "libs/local", "link": true }
+  }
+}"#;
+
+const CARGO_LOCK: &str = r#"version = 3
+
+[[package]]
+name = "tool"
+version = "0.1.0"
+dependencies = ["serde", "itoa 1.0.11"]
+
+[[package]]
+name = "serde"
+version = "1.0.200"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["serde_derive"]
+
+[[package]]
+name = "serde_derive"
+version = "1.0.200"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "itoa"
+version = "1.0.11"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+const POETRY_LOCK: &str = r#"[[package]]
+name = "Django"
+version = "4.2.1"
+
+[[package]]
+name = "sqlparse"
+version = "0.4.4"
+"#;
+
+const PYPROJECT: &str = r#"[tool.poetry]
+name = "api"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+django = "^4.2"
+"#;
+
+const REQUIREMENTS: &str = "\
+requests==2.31.0
+    # via -r requirements.in
+urllib3==2.0.7 \\
+    --hash=sha256:abc
+    # via
+    #   requests
+certifi==2024.2.2  # via requests
+";
+
+const GO_MOD: &str = "\
+module example.com/svc
+
+go 1.21
+
+require github.com/gin-gonic/gin v1.9.1
+
+require (
+\tgolang.org/x/net v0.17.0 // indirect
+\tgithub.com/stretchr/testify v1.8.4
+)
+";
+
+const ADVISORIES: &str = r#"[
+  {
+    "id": "GHSA-29mw-wpgm-hmr9",
+    "ecosystem": "npm",
+    "package": "lodash",
+    "versions": ["4.17.20"],
+    "severity": "moderate",
+    "summary": "ReDoS in lodash",
+    "url": "https://github.com/advisories/GHSA-29mw-wpgm-hmr9"
+  },
+  {
+    "id": "PYSEC-2023-100",
+    "ecosystem": "PyPI",
+    "package": "django",
+    "versions": ["4.2.0"],
+    "severity": "high"
+  }
+]"#;
+
+/// Monorepo with one project per supported ecosystem, plus a second npm
+/// project sharing `lodash` with `web`.
+fn monorepo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    write(root, "web/package.json", PACKAGE_JSON);
+    write(root, "web/package-lock.json", PACKAGE_LOCK_V3);
+    write(
+        root,
+        "admin/package.json",
+        r#"{ "name": "admin", "dependencies": { "react": "18.2.0" } }"#,
+    );
+    write(
+        root,
+        "admin/package-lock.json",
+        r#"{ "lockfileVersion": 3, "packages": {
+            "": { "dependencies": { "react": "18.2.0" } },
+            "node_modules/react": { "version": "18.2.0" },
+            "node_modules/lodash": { "version": "4.17.20" } } }"#,
+    );
+    write(root, "tool/Cargo.toml", "[package]\nname = \"tool\"\n");
+    write(root, "tool/Cargo.lock", CARGO_LOCK);
+    write(root, "api/pyproject.toml", PYPROJECT);
+    write(root, "api/poetry.lock", POETRY_LOCK);
+    write(root, "api/requirements.txt", "django==4.2.1\n");
+    write(root, "scripts/requirements.txt", REQUIREMENTS);
+    write(root, "svc/go.mod", GO_MOD);
+    write(root, "advisories.json", ADVISORIES);
+    dir
+}
+
+fn packages_in(dir: &TempDir, file: &str) -> Vec<LockedPackage> {
+    let root = dir.path().canonicalize().unwrap();
+    lockfile::parse_lockfile(&root.join(file), &root)
+        .unwrap()
+        .unwrap()
+}
+
+fn direct_names(packages: &[LockedPackage]) -> Vec<&str> {
+    let mut names: Vec<&str> = packages
+        .iter()
+        .filter(|p| p.direct)
+        .map(|p| p.name.as_str())
+        .collect();
+    names.sort_unstable();
+    names
+}
+
+fn sbom_for(dir: &TempDir) -> Sbom {
+    let mut config = RevetConfig::default();
+    config.modules.dependency_advisories = Some("advisories.json".to_string());
+    Sbom::from_repo(dir.path(), &config, None).unwrap()
+}
+
+fn render(sbom: &Sbom, format: SbomFormat) -> Value {
+    serde_json::from_str(&sbom.render(format).unwrap()).unwrap()
+}
+
+// ── Lockfile parsers ─────────────────────────────────────────────
+
+#[test]
+fn test_npm_lock_v3_direct_and_nested() {
+    let dir = monorepo();
+    let packages = packages_in(&dir, "web/package-lock.json");
+
+    assert_eq!(packages.len(), 3, "{:?}", packages);
+    assert_eq!(direct_names(&packages), vec!["lodash"]);
+    let semver = packages.iter().find(|p| p.name == "semver").unwrap();
+    assert!(!semver.direct);
+    assert_eq!(packages[0].project, "web");
+    assert_eq!(packages[0].manifest, Path::new("web/package.json"));
+}
+
+#[test]
+fn test_npm_lock_v1_nested_dependencies() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "package.json", PACKAGE_JSON);
+    write(
+        dir.path(),
+        "package-lock.json",
+        r#"{ "lockfileVersion": 1, "dependencies": {
+            "lodash": { "version": "4.17.21" },
+            "express": { "version": "4.18.2", "dependencies": { "debug": { "version": "2.6.9" } } },
+            "local": { "version": "file:../local" } } }"#,
+    );
+    let packages = packages_in(&dir, "package-lock.json");
+
+    assert_eq!(packages.len(), 3);
+    assert_eq!(direct_names(&packages), vec!["lodash"]);
+}
+
+#[test]
+fn test_cargo_lock_skips_local_crates() {
+    let dir = monorepo();
+    let packages = packages_in(&dir, "tool/Cargo.lock");
+
+    assert_eq!(packages.len(), 3);
+    assert!(packages.iter().all(|p| p.name != "tool"));
+    assert_eq!(direct_names(&packages), vec!["itoa", "serde"]);
+    assert_eq!(packages[0].project, "tool");
+}
+
+#[test]
+fn test_poetry_lock_uses_pyproject_for_direct() {
+    let dir = monorepo();
+    let packages = packages_in(&dir, "api/poetry.lock");
+
+    assert_eq!(direct_names(&packages), vec!["Django"]);
+    assert_eq!(packages[0].project, "api");
+    assert_eq!(packages[0].manifest, Path::new("api/pyproject.toml"));
+}
+
+#[test]
+fn test_requirements_via_annotations() {
+    let dir = monorepo();
+    let packages = packages_in(&dir, "scripts/requirements.txt");
+
+    assert_eq!(packages.len(), 3, "{:?}", packages);
+    assert_eq!(direct_names(&packages), vec!["requests"]);
+    assert_eq!(packages[0].project, "scripts");
+
+    let plain = revet_core::lockfile::python::parse_requirements("flask==3.0.0\nrich>=13\n");
+    assert_eq!(plain.packages.len(), 1);
+    assert!(plain.packages[0].direct);
+}
+
+#[test]
+fn test_go_mod_indirect() {
+    let dir = monorepo();
+    let packages = packages_in(&dir, "svc/go.mod");
+
+    assert_eq!(packages.len(), 3);
+    assert_eq!(
+        direct_names(&packages),
+        vec!["github.com/gin-gonic/gin", "github.com/stretchr/testify"]
+    );
+    assert_eq!(packages[0].project, "example.com/svc");
+}
+
+#[test]
+fn test_purls() {
+    let pkg = |ecosystem, name: &str, version: &str| LockedPackage {
+        ecosystem,
+        name: name.to_string(),
+        version: version.to_string(),
+        direct: true,
+        manifest: PathBuf::new(),
+        project: String::new(),
+    };
+    assert_eq!(
+        pkg(Ecosystem::Npm, "@babel/core", "7.24.0").purl(),
+        "pkg:npm/%40babel/core@7.24.0"
+    );
+    assert_eq!(
+        pkg(Ecosystem::PyPI, "Django_Rest.Framework", "3.0").purl(),
+        "pkg:pypi/django-rest-framework@3.0"
+    );
+    assert_eq!(
+        pkg(Ecosystem::Go, "github.com/gin-gonic/gin", "v1.9.1").purl(),
+        "pkg:golang/github.com/gin-gonic/gin@v1.9.1"
+    );
+    assert_eq!(
+        pkg(Ecosystem::Cargo, "serde", "1.0.0+build").purl(),
+        "pkg:cargo/serde@1.0.0%2Bbuild"
+    );
+}
+
+#[test]
+fn test_advisory_file_rejects_unknown_ecosystem() {
+    let dir = TempDir::new().unwrap();
+    let path = write(
+        dir.path(),
+        "adv.json",
+        r#"[{ "id": "X-1", "ecosystem": "hex", "package": "a", "versions": ["1"] }]"#,
+    );
+    let err = load_advisories(&path).unwrap_err();
+    assert!(err.to_string().contains("unknown ecosystem"));
+}
+
+// ── SBOM assembly ────────────────────────────────────────────────
+
+#[test]
+fn test_components_merged_across_projects() {
+    let dir = monorepo();
+    let sbom = sbom_for(&dir);
+
+    let lodash = sbom
+        .components
+        .iter()
+        .find(|c| c.purl == "pkg:npm/lodash@4.17.20")
+        .unwrap();
+    assert_eq!(
+        lodash.projects.keys().collect::<Vec<_>>(),
+        vec!["admin", "web"]
+    );
+    assert!(lodash.is_direct());
+    assert!(!lodash.projects["admin"].direct);
+
+    // poetry.lock supersedes requirements.txt in the same directory
+    assert_eq!(
+        sbom.components
+            .iter()
+            .filter(|c| c.purl.starts_with("pkg:pypi/django@"))
+            .count(),
+        1
+    );
+    let mut purls: Vec<_> = sbom.components.iter().map(|c| c.purl.clone()).collect();
+    purls.sort();
+    assert_eq!(
+        purls,
+        sbom.components
+            .iter()
+            .map(|c| c.purl.clone())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_advisories_matched_by_exact_version() {
+    let dir = monorepo();
+    let sbom = sbom_for(&dir);
+
+    assert_eq!(sbom.vulnerabilities.len(), 1);
+    assert_eq!(sbom.vulnerabilities[0].advisory.id, "GHSA-29mw-wpgm-hmr9");
+    assert_eq!(
+        sbom.vulnerabilities[0].affects,
+        vec!["pkg:npm/lodash@4.17.20"]
+    );
+}
+
+#[test]
+fn test_deterministic_output() {
+    let dir = monorepo();
+    let a = sbom_for(&dir);
+    let b = sbom_for(&dir);
+    for format in [SbomFormat::CycloneDx, SbomFormat::SpdxJson] {
+        assert_eq!(a.render(format).unwrap(), b.render(format).unwrap());
+    }
+
+    let packages = lockfile::collect(dir.path(), &[]).unwrap();
+    let mut reversed = packages.clone();
+    reversed.reverse();
+    let forward = Sbom::new("repo", Some("abc".into()), 0, packages, &[]);
+    let backward = Sbom::new("repo", Some("abc".into()), 0, reversed, &[]);
+    assert_eq!(
+        forward.render(SbomFormat::CycloneDx).unwrap(),
+        backward.render(SbomFormat::CycloneDx).unwrap()
+    );
+}
+
+#[test]
+fn test_serial_number_derived_from_commit() {
+    let at = |commit: &str| Sbom::new("repo", Some(commit.to_string()), 0, vec![], &[]).uuid();
+    assert_eq!(at("1111"), at("1111"));
+    assert_ne!(at("1111"), at("2222"));
+
+    let dir = monorepo();
+    let repo = git2::Repository::init(dir.path()).unwrap();
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig =
+        git2::Signature::new("t", "t@example.com", &git2::Time::new(1_700_000_000, 0)).unwrap();
+    let oid = repo
+        .commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+        .unwrap();
+
+    let sbom = sbom_for(&dir);
+    assert_eq!(sbom.commit.as_deref(), Some(oid.to_string().as_str()));
+    assert_eq!(sbom.timestamp(), "2023-11-14T22:13:20Z");
+}
+
+// ── Schema conformance ───────────────────────────────────────────
+//
+// The CycloneDX 1.5 and SPDX 2.3 JSON schemas are vendored under
+// tests/fixtures/sbom. They cannot say that references point at an element
+// of the document, so `dangling_refs` checks that.
+
+fn schema_errors(schema: &str, doc: &Value) -> Vec<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/sbom")
+        .join(schema);
+    let schema: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let schema = JSONSchema::options()
+        .with_draft(Draft::Draft7)
+        .compile(&schema)
+        .unwrap_or_else(|e| panic!("{}: invalid schema: {}", path.display(), e));
+    let mut errors = Vec::new();
+    if let Err(invalid) = schema.validate(doc) {
+        errors.extend(invalid.map(|e| format!("{} at {}", e, e.instance_path)));
+    }
+    errors
+}
+
+/// References to no element of the document, and elements sharing an ID
+fn dangling_refs(doc: &Value, format: SbomFormat) -> Vec<String> {
+    let list = |v: &Value| v.as_array().cloned().unwrap_or_default();
+    let (ids, refs): (Vec<Value>, Vec<Value>) = match format {
+        SbomFormat::CycloneDx => (
+            list(&doc["components"])
+                .iter()
+                .chain(std::iter::once(&doc["metadata"]["component"]))
+                .map(|c| c["bom-ref"].clone())
+                .collect(),
+            list(&doc["vulnerabilities"])
+                .iter()
+                .flat_map(|v| list(&v["affects"]))
+                .map(|a| a["ref"].clone())
+                .collect(),
+        ),
+        SbomFormat::SpdxJson => (
+            list(&doc["packages"])
+                .iter()
+                .map(|p| p["SPDXID"].clone())
+                .chain(std::iter::once(doc["SPDXID"].clone()))
+                .collect(),
+            list(&doc["relationships"])
+                .iter()
+                .flat_map(|r| [r["spdxElementId"].clone(), r["relatedSpdxElement"].clone()])
+                .collect(),
+        ),
+    };
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+    for id in &ids {
+        if !seen.insert(id) {
+            errors.push(format!("{} is not unique", id));
+        }
+    }
+    for r in &refs {
+        if !seen.contains(r) {
+            errors.push(format!("{} is dangling", r));
+        }
+    }
+    errors
+}
+
+#[test]
+fn test_cyclonedx_conforms_to_1_5_schema() {
+    let dir = monorepo();
+    let doc = render(&sbom_for(&dir), SbomFormat::CycloneDx);
+
+    let errors = schema_errors("bom-1.5.schema.json", &doc);
+    assert!(errors.is_empty(), "{:#?}", errors);
+    let errors = dangling_refs(&doc, SbomFormat::CycloneDx);
+    assert!(errors.is_empty(), "{:#?}", errors);
+
+    let vulns = doc["vulnerabilities"].as_array().unwrap();
+    assert_eq!(vulns.len(), 1);
+    assert_eq!(vulns[0]["ratings"][0]["severity"], "medium");
+    assert_eq!(vulns[0]["affects"][0]["ref"], "pkg:npm/lodash@4.17.20");
+}
+
+#[test]
+fn test_spdx_conforms_to_2_3_schema() {
+    let dir = monorepo();
+    let doc = render(&sbom_for(&dir), SbomFormat::SpdxJson);
+
+    let errors = schema_errors("spdx-2.3.schema.json", &doc);
+    assert!(errors.is_empty(), "{:#?}", errors);
+    let errors = dangling_refs(&doc, SbomFormat::SpdxJson);
+    assert!(errors.is_empty(), "{:#?}", errors);
+
+    let lodash = doc["packages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["name"] == "lodash")
+        .unwrap();
+    let refs = lodash["externalRefs"].as_array().unwrap();
+    assert!(refs.iter().any(|r| r["referenceCategory"] == "SECURITY"
+        && r["referenceLocator"] == "https://github.com/advisories/GHSA-29mw-wpgm-hmr9"));
+
+    let direct_edges = doc["relationships"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|r| r["relationshipType"] == "DEPENDS_ON" && r["comment"] == "direct")
+        .count();
+    // web: lodash; admin: react; tool: serde, itoa; api: Django; scripts: requests; svc: 2
+    assert_eq!(direct_edges, 8);
+}
+
+#[test]
+fn test_empty_repository_still_valid() {
+    let dir = TempDir::new().unwrap();
+    let sbom = Sbom::from_repo(dir.path(), &RevetConfig::default(), None).unwrap();
+    assert!(sbom.components.is_empty());
+    let cyclonedx = render(&sbom, SbomFormat::CycloneDx);
+    assert!(schema_errors("bom-1.5.schema.json", &cyclonedx).is_empty());
+    let spdx = render(&sbom, SbomFormat::SpdxJson);
+    assert!(schema_errors("spdx-2.3.schema.json", &spdx).is_empty());
+}
+
+#[test]
+fn test_schemas_reject_invalid_documents() {
+    let dir = monorepo();
+    let mut doc = render(&sbom_for(&dir), SbomFormat::CycloneDx);
+    doc["components"][0]["scope"] = "runtime".into();
+    doc["vulnerabilities"][0]["affects"][0]["ref"] = "pkg:npm/missing@1".into();
+    assert!(!schema_errors("bom-1.5.schema.json", &doc).is_empty());
+    assert!(!dangling_refs(&doc, SbomFormat::CycloneDx).is_empty());
+
+    let mut doc = render(&sbom_for(&dir), SbomFormat::SpdxJson);
+    doc["relationships"][0]["relationshipType"] = "USES".into();
+    doc["packages"][0]["SPDXID"] = "SPDXRef-Package-1".into();
+    assert!(!schema_errors("spdx-2.3.schema.json", &doc).is_empty());
+    assert!(!dangling_refs(&doc, SbomFormat::SpdxJson).is_empty());
+}
@@ -0,0 +1,277 @@
+//! Integration tests for budget-aware scheduling (`--time-budget`)
+
+use revet_core::config::RevetConfig;
+use revet_core::schedule::{self, parse_budget, Phase, PhaseTiming, Stage};
+use revet_core::{Scheduler, StoragePaths, Timings};
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Timings a previous run over 100 files stored
+fn stored() -> Timings {
+    let mut timings = Timings::default();
+    for (key, secs) in [
+        ("security", 20.0),
+        ("ml", 5.0),
+        ("graph", 60.0),
+        ("dead_code", 30.0),
+        ("complexity", 10.0),
+        ("duplication", 190.0),
+        ("hotspots", 40.0),
+    ] {
+        timings
+            .phases
+            .insert(key.to_string(), PhaseTiming { secs, files: 100 });
+    }
+    timings
+}
+
+fn scheduler(budget_secs: u64) -> Scheduler {
+    Scheduler::new(Some(Duration::from_secs(budget_secs)), Some(stored()), 100)
+}
+
+/// Labels of the skipped phases
+fn skipped(scheduler: &Scheduler) -> Vec<&str> {
+    scheduler
+        .skipped()
+        .iter()
+        .map(|s| s.phase.as_str())
+        .collect()
+}
+
+/// Walk the stages of a review the way `revet review` does; returns the
+/// keys of the phases that ran
+fn run_stages(scheduler: &mut Scheduler) -> Vec<String> {
+    let mut config = RevetConfig::default();
+    config.modules.duplication = true;
+    config.modules.dead_code = true;
+    config.modules.complexity = true;
+    let (content, deferred) = scheduler.admit_content(&["security", "ml", "duplication"], &config);
+    let mut ran: Vec<String> = ["security", "ml", "duplication"]
+        .into_iter()
+        .filter(|m| enabled(&content, m))
+        .map(String::from)
+        .collect();
+
+    let graph = Phase::new(schedule::GRAPH, Stage::Graph);
+    let mut scans: Vec<Phase> = deferred
+        .iter()
+        .map(|m| Phase::new(*m, Stage::Scans))
+        .collect();
+    if scheduler.admit(&graph) {
+        ran.push(schedule::GRAPH.to_string());
+        let graph_config = scheduler.admit_graph(&["dead_code", "complexity"], &config);
+        ran.extend(
+            ["dead_code", "complexity"]
+                .into_iter()
+                .filter(|m| enabled(&graph_config, m))
+                .map(String::from),
+        );
+        scans.push(Phase::new("hotspots", Stage::Scans));
+    } else {
+        for module in ["dead_code", "complexity", "hotspots"] {
+            let stage = if module == "hotspots" {
+                Stage::Scans
+            } else {
+                Stage::GraphAnalyzers
+            };
+            scheduler.skip_dependent(&Phase::new(module, stage), &graph);
+        }
+    }
+    ran.extend(
+        scheduler
+            .admit_all(scans, false)
+            .into_iter()
+            .map(|phase| phase.key),
+    );
+    ran.sort();
+    ran
+}
+
+fn enabled(config: &RevetConfig, module: &str) -> bool {
+    match module {
+        "security" => config.modules.security,
+        "ml" => config.modules.ml,
+        "duplication" => config.modules.duplication,
+        "dead_code" => config.modules.dead_code,
+        "complexity" => config.modules.complexity,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_ample_budget_runs_everything() {
+    let mut scheduler = scheduler(3600);
+    assert_eq!(
+        run_stages(&mut scheduler),
+        vec![
+            "complexity",
+            "dead_code",
+            "duplication",
+            "graph",
+            "hotspots",
+            "ml",
+            "security"
+        ]
+    );
+    let report = scheduler.report().unwrap();
+    assert!(!report.budget_exceeded);
+    assert!(report.skipped_phases.is_empty());
+    assert_eq!(report.time_budget_secs, 3600.0);
+}
+
+#[test]
+fn test_tight_budget_skips_the_expensive_scan() {
+    let mut scheduler = scheduler(200);
+    let ran = run_stages(&mut scheduler);
+    assert!(!ran.contains(&"duplication".to_string()), "{:?}", ran);
+    assert!(ran.contains(&"hotspots".to_string()));
+    assert!(ran.contains(&"dead_code".to_string()));
+    assert_eq!(skipped(&scheduler), vec!["duplication analyzer"]);
+
+    // Hotspots (40s) were admitted first, leaving about 160s
+    let skip = &scheduler.skipped()[0];
+    assert_eq!(skip.estimated_secs, 190.0);
+    assert!((159.0..=160.0).contains(&skip.remaining_secs), "{:?}", skip);
+    assert!(skip
+        .to_string()
+        .starts_with("duplication analyzer, estimated 190s, "));
+    assert!(scheduler.report().unwrap().budget_exceeded);
+}
+
+#[test]
+fn test_small_budget_keeps_content_analyzers_only() {
+    let mut scheduler = scheduler(50);
+    let ran = run_stages(&mut scheduler);
+    assert_eq!(ran, vec!["ml", "security"]);
+    assert_eq!(
+        skipped(&scheduler),
+        vec![
+            "code graph",
+            "dead code analyzer",
+            "complexity analyzer",
+            "hotspot ranking",
+            "duplication analyzer"
+        ]
+    );
+}
+
+#[test]
+fn test_zero_budget_skips_every_phase_but_still_reports() {
+    let mut scheduler = scheduler(0);
+    assert!(run_stages(&mut scheduler).is_empty());
+    assert_eq!(
+        skipped(&scheduler),
+        vec![
+            "ml analyzer",
+            "security analyzer",
+            "code graph",
+            "dead code analyzer",
+            "complexity analyzer",
+            "hotspot ranking",
+            "duplication analyzer"
+        ]
+    );
+    let report = scheduler.report().unwrap();
+    assert!(report.budget_exceeded);
+    assert_eq!(report.time_budget_secs, 0.0);
+}
+
+#[test]
+fn test_no_budget_admits_everything_in_place() {
+    let mut scheduler = Scheduler::new(None, Some(stored()), 100);
+    let (config, deferred) =
+        scheduler.admit_content(&["security", "duplication"], &RevetConfig::default());
+    assert!(deferred.is_empty());
+    assert!(config.modules.security);
+    assert!(scheduler.admit(&Phase::new(schedule::GRAPH, Stage::Graph)));
+    assert!(!scheduler.skipped_any());
+    assert!(scheduler.report().is_none());
+}
+
+#[test]
+fn test_skipped_content_module_is_switched_off() {
+    let mut timings = stored();
+    timings.phases.insert(
+        "ml".to_string(),
+        PhaseTiming {
+            secs: 500.0,
+            files: 100,
+        },
+    );
+    let mut scheduler = Scheduler::new(Some(Duration::from_secs(100)), Some(timings), 100);
+    let mut config = RevetConfig::default();
+    config.modules.ml = true;
+    config.modules.duplication = true;
+    let (config, deferred) = scheduler.admit_content(&["security", "ml", "duplication"], &config);
+    assert!(config.modules.security);
+    assert!(!config.modules.ml);
+    // Deferred to the scans, not skipped
+    assert!(!config.modules.duplication);
+    assert_eq!(deferred, vec!["duplication"]);
+    assert_eq!(skipped(&scheduler), vec!["ml analyzer"]);
+}
+
+#[test]
+fn test_dependent_phase_names_what_it_needs() {
+    let mut scheduler = scheduler(10);
+    let graph = Phase::new(schedule::GRAPH, Stage::Graph);
+    assert!(!scheduler.admit(&graph));
+    scheduler.skip_dependent(&Phase::new("dead_code", Stage::GraphAnalyzers), &graph);
+    assert_eq!(
+        scheduler.skipped()[1].to_string(),
+        "dead code analyzer, needs the code graph"
+    );
+}
+
+#[test]
+fn test_estimates_scale_with_files_and_fall_back_to_static() {
+    let scheduler = Scheduler::new(Some(Duration::from_secs(60)), Some(stored()), 200);
+    let estimate = |key: &str, stage| scheduler.estimate(&Phase::new(key, stage)).as_secs_f64();
+    assert_eq!(estimate("graph", Stage::Graph), 120.0);
+    assert_eq!(estimate("security", Stage::Content), 40.0);
+    // Hotspots follow the history, not the file count
+    assert_eq!(estimate("hotspots", Stage::Scans), 40.0);
+    // Never ran: a static estimate, slower per file for the graph build
+    let first = Scheduler::new(Some(Duration::from_secs(60)), None, 1000);
+    let graph = first.estimate(&Phase::new(schedule::GRAPH, Stage::Graph));
+    let content = first.estimate(&Phase::new("security", Stage::Content));
+    assert!(graph > content && content > Duration::ZERO);
+    assert!(
+        first.estimate(&Phase::new(schedule::HISTORY, Stage::Scans)) >= Duration::from_secs(60)
+    );
+}
+
+#[test]
+fn test_timings_round_trip_and_keep_phases_that_did_not_run() {
+    let dir = TempDir::new().unwrap();
+    let paths = StoragePaths::in_repo(dir.path());
+    assert!(Timings::load(&paths).is_none());
+
+    let mut scheduler = Scheduler::new(None, Some(stored()), 50);
+    scheduler.record("security", Duration::from_secs(2));
+    scheduler.record("security", Duration::from_secs(1));
+    scheduler.timings().save(&paths).unwrap();
+
+    let loaded = Timings::load(&paths).unwrap();
+    assert_eq!(
+        loaded.phases["security"],
+        PhaseTiming {
+            secs: 3.0,
+            files: 50
+        }
+    );
+    assert_eq!(loaded.phases["duplication"].secs, 190.0);
+    assert_eq!(loaded.phases["duplication"].files, 100);
+}
+
+#[test]
+fn test_parse_budget() {
+    assert_eq!(parse_budget("300"), Ok(Duration::from_secs(300)));
+    assert_eq!(parse_budget("300s"), Ok(Duration::from_secs(300)));
+    assert_eq!(parse_budget("5m"), Ok(Duration::from_secs(300)));
+    assert_eq!(parse_budget("1.5h"), Ok(Duration::from_secs(5400)));
+    assert_eq!(parse_budget("0"), Ok(Duration::ZERO));
+    for bad in ["", "soon", "5d", "-1s", "m"] {
+        assert!(parse_budget(bad).is_err(), "{:?}", bad);
+    }
+}
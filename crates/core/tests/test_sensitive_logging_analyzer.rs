@@ -3,8 +3,9 @@
 use revet_core::analyzer::sensitive_logging::SensitiveLoggingAnalyzer;
 use revet_core::analyzer::Analyzer;
 use revet_core::config::RevetConfig;
-use revet_core::finding::Severity;
-use std::path::PathBuf;
+use revet_core::finding::{Confidence, Severity};
+use revet_core::{AnalyzerDispatcher, Finding};
+use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 
 fn write_temp_file(dir: &TempDir, name: &str, content: &str) -> PathBuf {
@@ -20,6 +21,14 @@ fn analyzer() -> SensitiveLoggingAnalyzer {
     SensitiveLoggingAnalyzer::new()
 }
 
+fn run(file: &str, source: &str) -> Vec<Finding> {
+    analyzer().analyze_source(Path::new(file), source, Path::new("/repo"))
+}
+
+fn lines(findings: &[Finding]) -> Vec<usize> {
+    findings.iter().map(|f| f.line).collect()
+}
+
 // ── Python logging ────────────────────────────────────────────────
 
 #[test]
@@ -258,3 +267,248 @@ fn test_one_finding_per_line() {
     let findings = analyzer().analyze_files(&[file], dir.path());
     assert_eq!(findings.len(), 1, "one finding per line; got: {findings:?}");
 }
+
+// ── Argument inspection ───────────────────────────────────────────
+
+#[test]
+fn test_python_interpolated_pii_and_members() {
+    let findings = run(
+        "views.py",
+        r#"
+logger.info(f"welcome email sent to {user.email}")
+self._logger.warning("reset for %s", customer_email)
+log.debug("auth header: " + request.headers["Authorization"])
+logging.info("card=%s", payment.card_number)
+"#,
+    );
+    assert_eq!(lines(&findings), vec![2, 3, 4, 5], "{:?}", findings);
+    assert_eq!(
+        findings[0].message,
+        "Sensitive data in log: `user.email` passed to Python logging call"
+    );
+    assert_eq!(findings[0].severity, Severity::Warning);
+    assert_eq!(findings[0].confidence, Confidence::Medium);
+}
+
+#[test]
+fn test_whole_objects() {
+    let findings = run(
+        "handler.ts",
+        r#"
+console.log(req);
+this.logger.info("current user", currentUser);
+logger.debug({ ctx });
+"#,
+    );
+    assert_eq!(lines(&findings), vec![2, 3, 4], "{:?}", findings);
+    assert_eq!(
+        findings[0].message,
+        "Sensitive data in log: entire `req` object passed to console.*"
+    );
+    assert!(findings[0]
+        .suggestion
+        .as_deref()
+        .unwrap()
+        .contains("user.id"));
+}
+
+#[test]
+fn test_exception_with_raw_body() {
+    let findings = run(
+        "handler.js",
+        r#"
+console.error(err, req.body);
+console.error("failed", req.body);
+logger.error(err);
+"#,
+    );
+    assert_eq!(lines(&findings), vec![2], "{:?}", findings);
+    assert_eq!(
+        findings[0].message,
+        "Sensitive data in log: exception logged with raw body `req.body` in console.*"
+    );
+
+    let findings = run(
+        "views.py",
+        "logger.exception(\"bad payload %s\", request.data)\nlogger.error(\"bad\", exc_info=True, extra={\"body\": raw_body})\n",
+    );
+    assert_eq!(lines(&findings), vec![1, 2], "{:?}", findings);
+}
+
+#[test]
+fn test_go_and_java_call_sites() {
+    let go = run(
+        "handler.go",
+        r#"package main
+
+func handle(w http.ResponseWriter, r *http.Request) {
+	log.Printf("login %s", user.Email)
+	s.logger.Infof("auth %s", r.Header.Get("Authorization"))
+	log.Println(err, r.Body)
+	slog.Info("request", "ctx", ctx)
+}
+"#,
+    );
+    assert_eq!(lines(&go), vec![4, 5, 6, 7], "{:?}", go);
+    assert!(go[1].message.contains("Go logger call"));
+
+    let java = run(
+        "AuthService.java",
+        r#"class AuthService {
+    void login(User user) {
+        LOGGER.info("login for {}", user.getEmail());
+        this.log.warn("token " + session.token);
+        System.err.println(request);
+    }
+}
+"#,
+    );
+    assert_eq!(lines(&java), vec![3, 4, 5], "{:?}", java);
+    assert!(java[0].message.contains("SLF4J logger call"));
+}
+
+#[test]
+fn test_rust_macros() {
+    let findings = run(
+        "auth.rs",
+        r#"fn login(user: &User, token: &str) {
+    tracing::info!(email = %user.email, "login");
+    info!("issued {token}");
+    println!("{:?}", user);
+    tracing::debug!(token_len = token.len(), user_id = user.id, "issued");
+    log::warn!("login for {}", mask(&user.email));
+}
+"#,
+    );
+    assert_eq!(lines(&findings), vec![2, 3, 4], "{:?}", findings);
+    assert_eq!(
+        findings[0].message,
+        "Sensitive data in log: `user.email` passed to tracing/log macro"
+    );
+}
+
+#[test]
+fn test_sanitized_patterns_pass_untouched() {
+    let py = run(
+        "views.py",
+        r#"
+logger.info("sent to %s", mask(email))
+logger.info("user %s", user.id)
+logger.info("token length %d", len(token))
+logger.debug("has token: %s", has_token)
+logger.error("failed", exc_info=True)
+logger.info("masked: %s", masked_email)
+logger.info("password reset requested")
+print(f"{token_count} tokens issued")
+"#,
+    );
+    assert!(py.is_empty(), "{:?}", py);
+
+    let ts = run(
+        "handler.ts",
+        r#"
+console.log("token length", token.length);
+logger.info({ userId: user.id, method: req.method });
+console.log(typeof token);
+logger.warn(`email verified: ${isEmailVerified}`);
+console.error("failed", err);
+logger.info(redactEmail(email));
+"#,
+    );
+    assert!(ts.is_empty(), "{:?}", ts);
+
+    let go = run(
+        "handler.go",
+        r#"package main
+
+func handle(r *http.Request) {
+	log.Printf("%s %s", r.Method, r.URL.Path)
+	log.Printf("token length %d", len(token))
+	logger.Errorf("request failed: %v", err)
+}
+"#,
+    );
+    assert!(go.is_empty(), "{:?}", go);
+
+    let java = run(
+        "OrderService.java",
+        r#"class OrderService {
+    void place(Order order) {
+        log.info("order {}", order.getId());
+        log.debug("password valid: {}", isPasswordValid(input));
+        log.error("failed", e);
+    }
+}
+"#,
+    );
+    assert!(java.is_empty(), "{:?}", java);
+}
+
+#[test]
+fn test_offending_argument_columns() {
+    let findings = run("app.js", "console.log(\"pwd\", user.password);\n");
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].column, Some(20));
+    assert_eq!(findings[0].end_column, Some(33));
+
+    let findings = run("auth.rs", "info!(\"é {token}\");\n");
+    assert_eq!(findings[0].column, Some(11));
+    assert_eq!(findings[0].end_column, Some(16));
+
+    let findings = run("auth.php", "error_log(\"x\" . $password);\n");
+    assert_eq!(findings[0].column, Some(17));
+}
+
+// ── Configuration ─────────────────────────────────────────────────
+
+#[test]
+fn test_configured_names_add_and_remove() {
+    let source = "logger.info(\"%s %s\", iban, card)\nlogger.info(csrf_token)\n";
+    assert_eq!(lines(&run("pay.py", source)), vec![1, 2]);
+
+    let custom = SensitiveLoggingAnalyzer::with_names(
+        &["iban".to_string()],
+        &["card".to_string(), "csrf_token".to_string()],
+    );
+    let findings = custom.analyze_source(Path::new("pay.py"), source, Path::new("/repo"));
+    assert_eq!(lines(&findings), vec![1], "{:?}", findings);
+    assert!(findings[0].message.contains("`iban`"));
+}
+
+#[test]
+fn test_config_wiring_and_validation() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[sensitive_logging]
+allow_names = ["email"]
+"#,
+    )
+    .unwrap();
+    let dir = TempDir::new().unwrap();
+    let file = write_temp_file(&dir, "app.py", "logger.info(email)\nlogger.info(token)\n");
+    let findings =
+        AnalyzerDispatcher::new_with_config(&config).run_all(&[file], dir.path(), &config);
+    let log: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("LOG"))
+        .collect();
+    assert_eq!(log.len(), 1, "{:?}", log);
+    assert_eq!(log[0].line, 2);
+
+    let config: RevetConfig = toml::from_str(
+        r#"
+[sensitive_logging]
+sensitive_names = ["", "iban"]
+allow_names = [" "]
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    assert_eq!(
+        errors
+            .iter()
+            .filter(|e| e.contains("[sensitive_logging]"))
+            .count(),
+        2
+    );
+}
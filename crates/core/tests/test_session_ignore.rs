@@ -0,0 +1,152 @@
+//! Tests for session ignores: matching by finding key, rule and path,
+//! expiry, and where the entries are kept
+
+use revet_core::session_ignore::{parse_ttl, SessionIgnore, SessionIgnores, REASON};
+use revet_core::{Finding, Severity, StoragePaths};
+use std::path::Path;
+use std::time::Duration;
+use tempfile::TempDir;
+
+const ROOT: &str = "/repo";
+const NOW: u64 = 1_800_000_000;
+const HOUR: Duration = Duration::from_secs(3600);
+
+fn finding(id: &str, file: &str, line: usize, message: &str) -> Finding {
+    Finding {
+        id: id.to_string(),
+        severity: Severity::Warning,
+        message: message.to_string(),
+        file: Path::new(ROOT).join(file),
+        line,
+        ..Default::default()
+    }
+}
+
+fn findings() -> Vec<Finding> {
+    vec![
+        finding("SEC-001", "src/app.py", 3, "Hardcoded token"),
+        finding(
+            "CPLX-001",
+            "src/wip/draft.py",
+            10,
+            "Function `f` is too complex",
+        ),
+        finding("CPLX-002", "src/core.py", 20, "Function `g` is too complex"),
+        finding("SEC-002", "src/wip/draft.py", 4, "Hardcoded password"),
+    ]
+}
+
+fn ids(findings: &[Finding]) -> Vec<&str> {
+    findings.iter().map(|f| f.id.as_str()).collect()
+}
+
+fn apply(ignores: &SessionIgnores, now: u64) -> (Vec<String>, Vec<String>) {
+    let (kept, ignored) = ignores.apply(findings(), Path::new(ROOT), now);
+    assert!(ignored.iter().all(|s| s.reason == REASON));
+    (
+        ids(&kept).into_iter().map(String::from).collect(),
+        ignored.iter().map(|s| s.finding.id.clone()).collect(),
+    )
+}
+
+#[test]
+fn test_finding_entry_follows_the_finding_not_its_id_or_line() {
+    let recorded = finding("SEC-007", "src/app.py", 9, "Hardcoded token");
+    let ignores = SessionIgnores {
+        entries: vec![SessionIgnore::finding(
+            "SEC-007",
+            &recorded,
+            Path::new(ROOT),
+            NOW,
+            HOUR,
+        )],
+    };
+    let (kept, ignored) = apply(&ignores, NOW + 60);
+    assert_eq!(ignored, ["SEC-001"]);
+    assert_eq!(kept, ["CPLX-001", "CPLX-002", "SEC-002"]);
+}
+
+#[test]
+fn test_rule_and_path_entries() {
+    let rule_in_path = SessionIgnore::rule(Some("CPLX"), Some("src/wip/**"), NOW, HOUR).unwrap();
+    let (_, ignored) = apply(
+        &SessionIgnores {
+            entries: vec![rule_in_path],
+        },
+        NOW,
+    );
+    assert_eq!(ignored, ["CPLX-001"]);
+
+    let path = SessionIgnore::rule(None, Some("src/wip/**"), NOW, HOUR).unwrap();
+    let (_, ignored) = apply(
+        &SessionIgnores {
+            entries: vec![path],
+        },
+        NOW,
+    );
+    assert_eq!(ignored, ["CPLX-001", "SEC-002"]);
+
+    assert!(SessionIgnore::rule(None, None, NOW, HOUR).is_err());
+    assert!(SessionIgnore::rule(None, Some("src/[wip"), NOW, HOUR).is_err());
+}
+
+#[test]
+fn test_entries_expire_after_their_ttl() {
+    let mut ignores = SessionIgnores {
+        entries: vec![
+            SessionIgnore::rule(Some("SEC"), None, NOW, HOUR).unwrap(),
+            SessionIgnore::rule(Some("CPLX"), None, NOW, 8 * HOUR).unwrap(),
+        ],
+    };
+    assert_eq!(apply(&ignores, NOW + 1800).1.len(), 4);
+    assert_eq!(
+        ignores.entries[0].remaining(NOW + 1800),
+        Some(Duration::from_secs(1800))
+    );
+
+    // The SEC entry ran out at exactly one hour
+    assert_eq!(ignores.entries[0].remaining(NOW + 3600), None);
+    assert_eq!(apply(&ignores, NOW + 3600).0, ["SEC-001", "SEC-002"]);
+    assert!(ignores.is_active(NOW + 3600));
+
+    assert_eq!(ignores.prune(NOW + 3600), 1);
+    assert_eq!(ignores.entries.len(), 1);
+    assert!(!ignores.is_active(NOW + 8 * 3600));
+    assert_eq!(apply(&ignores, NOW + 8 * 3600).1.len(), 0);
+}
+
+#[test]
+fn test_parse_ttl() {
+    assert_eq!(parse_ttl("8h"), Ok(8 * HOUR));
+    assert_eq!(parse_ttl("30m"), Ok(Duration::from_secs(1800)));
+    assert!(parse_ttl("0h").is_err());
+    assert!(parse_ttl("1d").unwrap_err().contains("TTL"));
+}
+
+#[test]
+fn test_entries_round_trip_outside_the_repository() {
+    let repo = TempDir::new().unwrap();
+    let data = TempDir::new().unwrap();
+    let paths = StoragePaths::with_data_dir(repo.path(), Some(data.path().to_str().unwrap()), None);
+    let file = SessionIgnores::path(&paths).unwrap();
+    assert!(file.starts_with(data.path()));
+
+    let ignores = SessionIgnores {
+        entries: vec![SessionIgnore::rule(Some("CPLX"), Some("src/**"), NOW, HOUR).unwrap()],
+    };
+    ignores.save(&file).unwrap();
+    assert_eq!(SessionIgnores::load(&file).unwrap(), ignores);
+
+    // Saving none removes the file
+    SessionIgnores::default().save(&file).unwrap();
+    assert!(!file.exists());
+    assert_eq!(
+        SessionIgnores::load(&file).unwrap(),
+        SessionIgnores::default()
+    );
+
+    // Unrelocated state goes to the per-user state directory
+    if let Some(local) = SessionIgnores::path(&StoragePaths::in_repo(repo.path())) {
+        assert!(!local.starts_with(repo.path()), "{}", local.display());
+    }
+}
@@ -0,0 +1,388 @@
+//! Integration tests for SkewAnalyzer
+
+mod common;
+
+use revet_core::analyzer::skew::{AllowEntry, KeySpec, SkewAnalyzer};
+use revet_core::analyzer::Analyzer;
+use revet_core::config::{RevetConfig, RuleSelection, SkewConfig};
+use revet_core::finding::{Finding, Severity};
+use std::path::Path;
+use tempfile::TempDir;
+
+const FIXTURE: &str = "skew_monorepo";
+const EXTENSIONS: &[&str] = &[".json", ".toml"];
+
+/// (repo-relative file, line, sub-rule) for each SKEW finding, sorted
+fn summary(findings: &[Finding], root: &Path) -> Vec<(String, usize, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("SKEW"))
+        .map(|f| {
+            let file = f.file.strip_prefix(root).unwrap_or(&f.file);
+            (
+                file.to_string_lossy().replace('\\', "/"),
+                f.line,
+                f.rule.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+fn row(file: &str, line: usize, rule: &str) -> (String, usize, String) {
+    (file.to_string(), line, rule.to_string())
+}
+
+fn write(root: &Path, rel: &str, content: &str) {
+    let path = root.join(rel);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+fn package_json(root: &Path, dir: &str, deps: &str) {
+    write(
+        root,
+        &format!("{}/package.json", dir),
+        &format!(
+            "{{\n  \"name\": \"{}\",\n  \"dependencies\": {{ {} }}\n}}\n",
+            dir, deps
+        ),
+    );
+}
+
+fn analyze(analyzer: &SkewAnalyzer, root: &Path) -> Vec<Finding> {
+    analyzer.analyze_files(&common::discover(root, EXTENSIONS), root)
+}
+
+fn skew(toml: &str) -> SkewConfig {
+    let config: RevetConfig = toml::from_str(toml).unwrap();
+    config.analyzers.skew
+}
+
+// ── Fixture monorepo ─────────────────────────────────────────────
+
+#[test]
+fn test_fixture_monorepo() {
+    let root = common::fixture_root(FIXTURE);
+    let findings = common::run(&root, EXTENSIONS, &common::fixture_config(FIXTURE));
+
+    assert_eq!(
+        summary(&findings, &root),
+        vec![
+            row("crates/legacy/Cargo.toml", 4, "skew:config"),
+            row("packages/checkout/package.json", 5, "skew:versions"),
+            row("packages/checkout/tsconfig.json", 6, "skew:config"),
+            row("packages/mobile/package.json", 5, "skew:versions"),
+            row("services/ml/pyproject.toml", 4, "skew:config"),
+        ]
+    );
+    assert!(findings
+        .iter()
+        .filter(|f| f.id.starts_with("SKEW"))
+        .all(|f| f.severity == Severity::Warning));
+}
+
+#[test]
+fn test_fixture_messages_name_packages_and_versions() {
+    let root = common::fixture_root(FIXTURE);
+    let findings = common::run(&root, EXTENSIONS, &common::fixture_config(FIXTURE));
+    let finding = |file: &str| findings.iter().find(|f| f.file.ends_with(file)).unwrap();
+    let message = |file: &str| finding(file).message.clone();
+
+    assert_eq!(
+        message("packages/checkout/package.json"),
+        "packages/checkout wants react 18.2.0, 3 other packages use 18.3.1"
+    );
+    let table = &finding("packages/checkout/package.json")
+        .details
+        .as_ref()
+        .unwrap()
+        .version_table;
+    let rows: Vec<(&str, Vec<&str>)> = table
+        .iter()
+        .map(|r| {
+            (
+                r.version.as_str(),
+                r.used_by.iter().map(String::as_str).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            (
+                "18.3.1",
+                vec!["packages/admin", "packages/shop", "packages/web"]
+            ),
+            ("18.2.0", vec!["packages/checkout"]),
+            ("16.14.0", vec!["packages/mobile"]),
+        ]
+    );
+    assert!(message("packages/checkout/tsconfig.json").starts_with(
+        "packages/checkout sets compilerOptions.strict = false in tsconfig.json, 3 other packages use true"
+    ));
+    assert!(message("crates/legacy/Cargo.toml").starts_with(
+        "crates/legacy sets package.edition = 2018 in Cargo.toml, 2 other packages use 2021"
+    ));
+}
+
+#[test]
+fn test_fixture_without_exceptions() {
+    let root = common::fixture_root(FIXTURE);
+    let mut config = common::fixture_config(FIXTURE);
+    config.analyzers.skew.allow.clear();
+    let findings = common::run(&root, EXTENSIONS, &config);
+
+    let legacy: Vec<&Finding> = findings
+        .iter()
+        .filter(|f| f.file.starts_with(root.join("packages/legacy-admin")))
+        .collect();
+    assert_eq!(legacy.len(), 2, "react and strict both flagged");
+    assert!(legacy[0]
+        .details
+        .as_ref()
+        .unwrap()
+        .version_table
+        .iter()
+        .any(|r| r.version == "17.0.2" && r.used_by == ["packages/legacy-admin"]));
+    assert!(legacy[0]
+        .suggestion
+        .as_deref()
+        .unwrap()
+        .contains("\"react@17.0.2:packages/legacy-admin\""));
+}
+
+#[test]
+fn test_fixture_sub_rule_selection() {
+    let root = common::fixture_root(FIXTURE);
+    let mut config = common::fixture_config(FIXTURE);
+    config.analyzers.skew.selection = RuleSelection {
+        only: vec!["versions".to_string()],
+        ..Default::default()
+    };
+    let rules: Vec<String> = summary(&common::run(&root, EXTENSIONS, &config), &root)
+        .into_iter()
+        .map(|(_, _, rule)| rule)
+        .collect();
+    assert_eq!(rules, vec!["skew:versions", "skew:versions"]);
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let root = common::fixture_root(FIXTURE);
+    let findings = common::run(&root, EXTENSIONS, &RevetConfig::default());
+    assert!(findings.iter().all(|f| !f.id.starts_with("SKEW")));
+}
+
+// ── Versions ─────────────────────────────────────────────────────
+
+#[test]
+fn test_max_versions_threshold() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    package_json(&root, "a", r#""lodash": "4.17.21""#);
+    package_json(&root, "b", r#""lodash": "^4.17.21""#);
+    package_json(&root, "c", r#""lodash": "4.17.20""#);
+
+    assert!(analyze(&SkewAnalyzer::new(), &root).is_empty());
+
+    let analyzer = SkewAnalyzer::with_config(&skew("[analyzers.skew]\nmax_versions = 1"));
+    let findings = analyze(&analyzer, &root);
+    assert_eq!(findings.len(), 1);
+    assert!(findings[0].file.ends_with("c/package.json"));
+    assert_eq!(findings[0].line, 3);
+}
+
+#[test]
+fn test_version_tie_goes_to_highest() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    package_json(&root, "a", r#""vue": "3.4.0""#);
+    package_json(&root, "b", r#""vue": "3.10.0""#);
+
+    let analyzer = SkewAnalyzer::with_config(&skew("[analyzers.skew]\nmax_versions = 1"));
+    let findings = analyze(&analyzer, &root);
+    assert_eq!(findings.len(), 1);
+    assert!(findings[0]
+        .message
+        .starts_with("a wants vue 3.4.0, 1 other package uses 3.10.0"));
+}
+
+#[test]
+fn test_non_plain_versions_are_skipped() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    package_json(&root, "a", r#""ui": "workspace:*""#);
+    package_json(&root, "b", r#""ui": "github:acme/ui#main""#);
+    package_json(&root, "c", r#""ui": ">=1.0.0 <2.0.0""#);
+    package_json(&root, "d", r#""ui": "1.2.0""#);
+
+    let analyzer = SkewAnalyzer::with_config(&skew("[analyzers.skew]\nmax_versions = 1"));
+    assert!(analyze(&analyzer, &root).is_empty());
+}
+
+#[test]
+fn test_python_names_are_normalized() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    write(
+        &root,
+        "a/pyproject.toml",
+        "[project]\nname = \"a\"\ndependencies = [\"Flask_Login==0.6.3\"]\n",
+    );
+    write(
+        &root,
+        "b/pyproject.toml",
+        "[tool.poetry.group.dev.dependencies]\nflask-login = { version = \"0.6.2\" }\n",
+    );
+
+    let analyzer = SkewAnalyzer::with_config(&skew("[analyzers.skew]\nmax_versions = 1"));
+    let findings = analyze(&analyzer, &root);
+    assert_eq!(findings.len(), 1);
+    assert!(findings[0].file.ends_with("b/pyproject.toml"));
+    assert_eq!(findings[0].line, 2);
+}
+
+#[test]
+fn test_cargo_workspace_inheritance_is_skipped() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    write(
+        &root,
+        "Cargo.toml",
+        "[workspace.dependencies]\nanyhow = \"1.0.80\"\n",
+    );
+    write(
+        &root,
+        "a/Cargo.toml",
+        "[package]\nname = \"a\"\n\n[dependencies]\nanyhow.workspace = true\n",
+    );
+    write(
+        &root,
+        "b/Cargo.toml",
+        "[package]\nname = \"b\"\n\n[dev-dependencies]\nanyhow = \"1.0.75\"\n",
+    );
+
+    let analyzer = SkewAnalyzer::with_config(&skew("[analyzers.skew]\nmax_versions = 1"));
+    let findings = analyze(&analyzer, &root);
+    assert_eq!(findings.len(), 1, "{findings:?}");
+    let table = &findings[0].details.as_ref().unwrap().version_table;
+    assert_eq!(table[0].version, "1.0.80");
+}
+
+// ── Config keys ──────────────────────────────────────────────────
+
+#[test]
+fn test_config_values_without_majority_flag_every_file() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    write(
+        &root,
+        "a/tsconfig.json",
+        "{ \"compilerOptions\": { \"strict\": true } }",
+    );
+    write(
+        &root,
+        "b/tsconfig.json",
+        "{ \"compilerOptions\": { \"strict\": false } }",
+    );
+    write(&root, "c/tsconfig.json", "{ \"compilerOptions\": {} }");
+
+    let findings = analyze(&SkewAnalyzer::new(), &root);
+    assert_eq!(findings.len(), 2);
+    assert!(findings
+        .iter()
+        .all(|f| f.message.contains("disagree with no majority")));
+}
+
+#[test]
+fn test_custom_keys_and_key_exceptions() {
+    let tmp = TempDir::new().unwrap();
+    let root = tmp.path().canonicalize().unwrap();
+    for (dir, node) in [("a", "20"), ("b", "20"), ("old", "16")] {
+        write(
+            &root,
+            &format!("{}/runtime.json", dir),
+            &format!("{{\"engines\": {{\"node\": \"{}\"}}}}", node),
+        );
+    }
+
+    let analyzer = SkewAnalyzer::with_config(&skew(
+        "[analyzers.skew]\nkeys = [\"runtime.json:engines.node\"]",
+    ));
+    let findings = analyze(&analyzer, &root);
+    assert_eq!(findings.len(), 1);
+    assert!(findings[0]
+        .message
+        .starts_with("old sets engines.node = 16"));
+
+    let analyzer = SkewAnalyzer::with_config(&skew(
+        "[analyzers.skew]\nkeys = [\"runtime.json:engines.node\"]\nallow = [\"engines.node@16:old\"]",
+    ));
+    assert!(analyze(&analyzer, &root).is_empty());
+}
+
+// ── Parsing and validation ───────────────────────────────────────
+
+#[test]
+fn test_allow_entry_parse() {
+    assert_eq!(
+        AllowEntry::parse("@types/node@18:packages/tools/"),
+        Some(AllowEntry {
+            name: "@types/node".to_string(),
+            version: "18".to_string(),
+            path: Some("packages/tools".to_string()),
+        })
+    );
+    assert_eq!(AllowEntry::parse("react@17").unwrap().path, None);
+    assert_eq!(AllowEntry::parse("react"), None);
+    assert_eq!(AllowEntry::parse("@react"), None);
+    assert_eq!(AllowEntry::parse("react@:packages/x"), None);
+    assert_eq!(AllowEntry::parse("react@17:"), None);
+}
+
+#[test]
+fn test_key_spec_parse() {
+    assert_eq!(
+        KeySpec::parse("tsconfig.json:compilerOptions.strict"),
+        Some(KeySpec {
+            file: "tsconfig.json".to_string(),
+            key: "compilerOptions.strict".to_string(),
+        })
+    );
+    assert_eq!(KeySpec::parse("tsconfig.json"), None);
+    assert_eq!(KeySpec::parse("setup.cfg:metadata.name"), None);
+    assert_eq!(
+        KeySpec::parse("tsconfig.json:compilerOptions..strict"),
+        None
+    );
+}
+
+#[test]
+fn test_validate_skew_config() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[analyzers.skew]
+max_versions = 0
+keys = ["setup.cfg:metadata.name"]
+allow = ["react"]
+only = ["version"]
+"#,
+    )
+    .unwrap();
+
+    let (errors, _) = config.validate();
+    for expected in [
+        "[analyzers.skew] max_versions",
+        "[analyzers.skew] keys entry \"setup.cfg:metadata.name\"",
+        "[analyzers.skew] allow entry \"react\"",
+        "[analyzers.skew] unknown sub-rule \"version\" for skew",
+    ] {
+        assert!(
+            errors.iter().any(|e| e.starts_with(expected)),
+            "{expected}: {errors:?}"
+        );
+    }
+    assert!(RevetConfig::default().validate().0.is_empty());
+}
@@ -3,7 +3,7 @@
 use revet_core::analyzer::sql_injection::SqlInjectionAnalyzer;
 use revet_core::analyzer::Analyzer;
 use revet_core::config::RevetConfig;
-use revet_core::finding::Severity;
+use revet_core::finding::{InsertContext, Severity};
 use std::path::PathBuf;
 use tempfile::TempDir;
 
@@ -481,3 +481,90 @@ query = f"UPDATE items SET name = {name} WHERE id = {iid}"
     assert_eq!(sql_findings[1].id, "SQL-002");
     assert_eq!(sql_findings[2].id, "SQL-003");
 }
+
+// ── Suggested code ──────────────────────────────────────────────
+
+/// The suggested code of the single finding in a file holding `content`
+fn suggested(name: &str, content: &str) -> Option<String> {
+    let dir = TempDir::new().unwrap();
+    let file = write_temp_file(&dir, name, content);
+    let findings = SqlInjectionAnalyzer::new().analyze_files(&[file], dir.path());
+    assert_eq!(findings.len(), 1, "{:?}", findings);
+    findings[0].suggestion_code.as_ref().map(|code| {
+        assert_eq!(code.insert_context, InsertContext::Line);
+        code.snippet.clone()
+    })
+}
+
+#[test]
+fn test_fstring_in_execute_suggests_parameters() {
+    assert_eq!(
+        suggested(
+            "db.py",
+            "    cursor.execute(f\"SELECT * FROM users WHERE id = {user_id}\")\n"
+        )
+        .as_deref(),
+        Some("cursor.execute(\"SELECT * FROM users WHERE id = ?\", (user_id,))")
+    );
+    assert_eq!(
+        suggested(
+            "db.py",
+            "rows = Book.objects.raw(f'SELECT * FROM books WHERE a = {a} AND b = {b.id}')\n"
+        )
+        .as_deref(),
+        Some("rows = Book.objects.raw('SELECT * FROM books WHERE a = %s AND b = %s', [a, b.id])")
+    );
+}
+
+#[test]
+fn test_template_literal_in_query_suggests_parameters() {
+    assert_eq!(
+        suggested(
+            "db.ts",
+            "const rows = await db.query(`SELECT * FROM t WHERE a = ${a} AND b = '${b}'`);\n"
+        )
+        .as_deref(),
+        Some("const rows = await db.query(`SELECT * FROM t WHERE a = ? AND b = '?'`, [a, b]);")
+    );
+}
+
+#[test]
+fn test_percent_format_in_execute_suggests_parameters() {
+    assert_eq!(
+        suggested(
+            "db.py",
+            "cursor.execute(\"SELECT * FROM t WHERE id = %s\" % user_id)\n"
+        )
+        .as_deref(),
+        Some("cursor.execute(\"SELECT * FROM t WHERE id = %s\", (user_id,))")
+    );
+    assert_eq!(
+        suggested(
+            "db.py",
+            "cursor.execute(\"SELECT * FROM t WHERE a = %s AND b = %s\" % params)\n"
+        )
+        .as_deref(),
+        Some("cursor.execute(\"SELECT * FROM t WHERE a = %s AND b = %s\", params)")
+    );
+}
+
+#[test]
+fn test_unsupported_interpolation_has_no_code() {
+    // Format specs, `%d` and concatenation keep the prose only
+    for (name, line) in [
+        (
+            "db.py",
+            "cursor.execute(f\"SELECT * FROM t WHERE id = {user_id:d}\")\n",
+        ),
+        (
+            "db.py",
+            "cursor.execute(\"SELECT * FROM t WHERE id = %d\" % n)\n",
+        ),
+        (
+            "db.py",
+            "cursor.execute(\"SELECT * FROM t WHERE id = \" + user_id)\n",
+        ),
+    ] {
+        assert_eq!(suggested(name, line), None, "{}", line);
+    }
+}
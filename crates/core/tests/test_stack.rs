@@ -0,0 +1,111 @@
+//! Stacked changes: `Change-Id` trailers, first-parent walks and checkouts
+//! of a change's commit
+
+mod common;
+
+use common::git;
+use revet_core::diff::stack::{self, change_id, StackChange};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const ID: &str = "I8473b95934b5732ac55d26311a706c9c2bde9940";
+
+fn commit(dir: &Path, file: &str, message: &str) -> String {
+    fs::write(dir.join(file), file).unwrap();
+    git(dir, &["add", file]);
+    git(dir, &["commit", "-q", "-m", message]);
+    git(dir, &["rev-parse", "HEAD"])
+}
+
+#[test]
+fn test_change_id_trailer() {
+    assert_eq!(
+        change_id(&format!("Fix parser\n\nChange-Id: {}\n", ID)).as_deref(),
+        Some(ID)
+    );
+    // Among other trailers, in any case
+    let message = format!(
+        "Fix parser\n\nLonger text.\n\nBug: 12\nchange-id: {}\nSigned-off-by: Dev <dev@example.com>\n",
+        ID
+    );
+    assert_eq!(change_id(&message).as_deref(), Some(ID));
+
+    // Only the last paragraph holds trailers
+    let message = format!("Fix parser\n\nChange-Id: {}\n\nMore text.\n", ID);
+    assert_eq!(change_id(&message), None);
+    // Not an ID
+    assert_eq!(change_id("Fix\n\nChange-Id: 8473b959\n"), None);
+    assert_eq!(change_id("Fix\n\nChange-Id: I\n"), None);
+    assert_eq!(change_id("Fix parser"), None);
+}
+
+#[test]
+fn test_walk_follows_first_parents_oldest_first() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path();
+    git(path, &["init", "-q", "-b", "main"]);
+    let base = commit(path, "base.txt", "base");
+    git(path, &["checkout", "-q", "-b", "side"]);
+    commit(path, "side.txt", "side");
+    git(path, &["checkout", "-q", "main"]);
+    let one = commit(path, "one.txt", &format!("One\n\nChange-Id: {}", ID));
+    git(
+        path,
+        &["merge", "-q", "--no-ff", "side", "-m", "Merge side"],
+    );
+    let merge = git(path, &["rev-parse", "HEAD"]);
+    let two = commit(path, "two.txt", "Two");
+
+    let changes = stack::walk(path, &base, "HEAD").unwrap();
+    let commits: Vec<&str> = changes.iter().map(|c| c.commit.as_str()).collect();
+    assert_eq!(commits, [one.as_str(), merge.as_str(), two.as_str()]);
+    assert_eq!(changes[0].parent, base);
+    assert_eq!(changes[0].subject, "One");
+    assert_eq!(changes[0].key(), ID);
+    assert_eq!(changes[2].parent, merge);
+    assert_eq!(changes[2].key(), two);
+
+    assert!(stack::walk(path, "HEAD", "HEAD").unwrap().is_empty());
+    assert!(stack::walk(path, "no-such-ref", "HEAD").is_err());
+}
+
+#[test]
+fn test_checkout_leaves_the_repository_alone() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path();
+    git(path, &["init", "-q", "-b", "main"]);
+    commit(path, "base.txt", "base");
+    let first = commit(path, "first.txt", "first");
+    fs::write(path.join("first.txt"), "edited").unwrap();
+    commit(path, "second.txt", "second");
+
+    let out = TempDir::new().unwrap();
+    let target = out.path().join("first");
+    stack::checkout(path, &first, &target).unwrap();
+    assert_eq!(
+        fs::read_to_string(target.join("first.txt")).unwrap(),
+        "first.txt"
+    );
+    assert!(!target.join("second.txt").exists());
+
+    let change = StackChange::resolve(path, &first).unwrap();
+    assert_eq!(change.label(), format!("{} first", &first[..7]));
+    assert_eq!(git(path, &["status", "--porcelain"]), "M first.txt");
+}
+
+#[test]
+fn test_refs_by_commit() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path();
+    git(path, &["init", "-q", "-b", "main"]);
+    let head = commit(path, "base.txt", "base");
+    git(path, &["update-ref", "refs/changes/34/1234/5", &head]);
+
+    let refs = stack::refs_by_commit(path, "refs/changes/*");
+    assert_eq!(
+        refs.get(&head).map(String::as_str),
+        Some("refs/changes/34/1234/5")
+    );
+    assert!(stack::refs_by_commit(&path.join("missing"), "refs/*").is_empty());
+}
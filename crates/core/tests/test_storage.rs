@@ -0,0 +1,163 @@
+//! Tests for state locations (`[storage]`, `[baseline]`, `REVET_DATA_DIR`)
+
+mod common;
+
+use common::git;
+use revet_core::storage::{repo_key, StoragePaths, BASELINE_FILE, RUNS_DIR};
+use revet_core::{Baseline, CodeGraph, GraphCache, GraphCacheMeta, RevetConfig};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+use tempfile::TempDir;
+
+fn clone_of(origin: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    git(dir.path(), &["remote", "add", "origin", origin]);
+    dir
+}
+
+fn meta() -> GraphCacheMeta {
+    GraphCacheMeta {
+        commit_hash: None,
+        timestamp: SystemTime::now(),
+        file_checksums: HashMap::new(),
+        revet_version: revet_core::VERSION.to_string(),
+    }
+}
+
+#[test]
+fn test_default_layout_is_in_repo() {
+    let paths = StoragePaths::in_repo(Path::new("/repo"));
+    assert!(!paths.is_relocated());
+    assert_eq!(paths.data_dir(), Path::new("/repo/.revet-cache"));
+    assert_eq!(
+        paths.baseline_path(),
+        Path::new("/repo/.revet-cache/baseline.json")
+    );
+    assert_eq!(
+        paths.write_path(RUNS_DIR),
+        Path::new("/repo/.revet-cache/runs")
+    );
+}
+
+#[test]
+fn test_data_dir_is_keyed_by_origin_else_path() {
+    let a = clone_of("git@example.com:acme/api.git");
+    let b = clone_of("git@example.com:acme/api.git");
+    let other = clone_of("git@example.com:acme/web.git");
+    let hash = |dir: &TempDir| repo_key(dir.path()).rsplit('-').next().unwrap().to_string();
+    assert_eq!(hash(&a), hash(&b));
+    assert_ne!(hash(&a), hash(&other));
+    assert_eq!(hash(&a).len(), 16);
+
+    // Without a remote, two directories never share state
+    let (c, d) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+    assert_ne!(hash(&c), hash(&d));
+
+    let root = a.path().canonicalize().unwrap();
+    let paths = StoragePaths::with_data_dir(&root, Some("/var/cache/revet"), None);
+    assert!(paths.is_relocated());
+    assert_eq!(
+        paths.data_dir(),
+        Path::new("/var/cache/revet").join(repo_key(&root))
+    );
+    assert_eq!(paths.baseline_path(), paths.data_dir().join(BASELINE_FILE));
+    assert!(
+        repo_key(&root).starts_with(&format!("{}-", root.file_name().unwrap().to_string_lossy()))
+    );
+}
+
+#[test]
+fn test_baseline_path_may_leave_the_tree() {
+    let root = Path::new("/repo");
+    let relative = StoragePaths::with_data_dir(root, None, Some("ci/baseline.json"));
+    assert!(!relative.is_relocated());
+    assert_eq!(
+        relative.baseline_path(),
+        Path::new("/repo/ci/baseline.json")
+    );
+    let absolute = StoragePaths::with_data_dir(root, Some("/data"), Some("/shared/b.json"));
+    assert_eq!(absolute.baseline_path(), Path::new("/shared/b.json"));
+
+    let config: RevetConfig = toml::from_str(
+        r#"
+[storage]
+data_dir = ""
+
+[baseline]
+path = ""
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    assert!(errors.iter().any(|e| e.contains("[storage] data_dir")));
+    assert!(errors.iter().any(|e| e.contains("[baseline] path")));
+}
+
+#[test]
+fn test_legacy_state_is_read_as_fallback() {
+    let repo = TempDir::new().unwrap();
+    let data = TempDir::new().unwrap();
+    let legacy = StoragePaths::in_repo(repo.path());
+    let graph = CodeGraph::new(repo.path().to_path_buf());
+    GraphCache::at(&legacy, Default::default())
+        .save(&graph, &meta())
+        .unwrap();
+    Baseline::from_findings(&[], repo.path(), Some("abc".to_string()))
+        .save_at(&legacy, &mut common::journal(repo.path()))
+        .unwrap();
+
+    let paths = StoragePaths::with_data_dir(repo.path(), data.path().to_str(), None);
+    assert!(GraphCache::at(&paths, Default::default())
+        .load()
+        .unwrap()
+        .is_some());
+    let baseline = Baseline::load_at(&paths).unwrap().unwrap();
+    assert_eq!(baseline.commit.as_deref(), Some("abc"));
+    assert_eq!(
+        paths.read_path("graph.meta.json"),
+        legacy.write_path("graph.meta.json")
+    );
+
+    // Written state wins over the legacy copy
+    Baseline::from_findings(&[], repo.path(), Some("def".to_string()))
+        .save_at(&paths, &mut common::journal(repo.path()))
+        .unwrap();
+    let baseline = Baseline::load_at(&paths).unwrap().unwrap();
+    assert_eq!(baseline.commit.as_deref(), Some("def"));
+    assert!(paths.baseline_path().starts_with(data.path()));
+}
+
+#[test]
+fn test_migrate_moves_legacy_state() {
+    let repo = TempDir::new().unwrap();
+    let data = TempDir::new().unwrap();
+    let legacy = repo.path().join(".revet-cache");
+    fs::create_dir_all(legacy.join("runs")).unwrap();
+    fs::write(legacy.join("runs/1.json"), "{}").unwrap();
+    fs::write(legacy.join("churn.json"), "old").unwrap();
+    fs::write(legacy.join("baseline.json"), "{}").unwrap();
+
+    let baseline = data.path().join("baselines/api.json");
+    let paths = StoragePaths::with_data_dir(repo.path(), data.path().to_str(), baseline.to_str());
+    paths.ensure_data_dir().unwrap();
+    fs::write(paths.write_path("churn.json"), "new").unwrap();
+
+    let report = paths.migrate().unwrap();
+    assert_eq!(report.moved, ["baseline.json", "runs"]);
+    assert_eq!(report.skipped, ["churn.json"]);
+    assert!(baseline.exists());
+    assert!(paths.write_path("runs/1.json").exists());
+    assert_eq!(
+        fs::read_to_string(paths.write_path("churn.json")).unwrap(),
+        "new"
+    );
+    // The skipped entry keeps the legacy directory alive
+    assert!(legacy.join("churn.json").exists());
+
+    fs::remove_file(legacy.join("churn.json")).unwrap();
+    assert!(paths.migrate().unwrap().moved.is_empty());
+    assert!(!legacy.exists());
+}
@@ -4,7 +4,9 @@
 
 use std::path::PathBuf;
 
-use revet_core::graph::{Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeKind, Parameter};
+use revet_core::graph::{
+    Edge, EdgeKind, EdgeMetadata, ImportKind, Node, NodeData, NodeKind, Parameter,
+};
 use revet_core::store::{reconstruct_graph, GraphStore, MemoryStore, StoreNodeId};
 use revet_core::CodeGraph;
 
@@ -603,6 +605,43 @@ fn test_reconstruct_graph_preserves_decorators() {
     }
 }
 
+#[test]
+fn test_reconstruct_graph_preserves_import_kinds() {
+    let mut graph = CodeGraph::new(PathBuf::from("/repo"));
+    let file = |name: &str| {
+        Node::new(
+            NodeKind::File,
+            name.to_string(),
+            PathBuf::from(name),
+            1,
+            NodeData::File {
+                language: "typescript".to_string(),
+                code_kind: Default::default(),
+            },
+        )
+    };
+    let a = graph.add_node(file("a.ts"));
+    let b = graph.add_node(file("b.ts"));
+    graph.add_edge(a, b, Edge::import(ImportKind::TypeOnly));
+    graph.add_edge(b, a, Edge::import(ImportKind::Runtime));
+
+    for (name, store) in create_stores() {
+        store.flush(&graph, "v1").unwrap();
+
+        let reconstructed = reconstruct_graph(&*store, "v1", &PathBuf::from("/repo")).unwrap();
+        let mut kinds: Vec<ImportKind> = reconstructed
+            .nodes()
+            .flat_map(|(id, _)| reconstructed.edges_from(id).map(|(_, e)| e.import_kind()))
+            .collect();
+        kinds.sort_by_key(|k| k.as_str());
+        assert_eq!(
+            kinds,
+            vec![ImportKind::Runtime, ImportKind::TypeOnly],
+            "[{name}] import kinds should round-trip"
+        );
+    }
+}
+
 #[test]
 fn test_reconstruct_empty_graph() {
     let graph = CodeGraph::new(PathBuf::from("/repo"));
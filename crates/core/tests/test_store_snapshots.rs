@@ -0,0 +1,298 @@
+//! Integration tests for incremental CozoStore snapshots: delta chains,
+//! lineage and `gc`
+
+#![cfg(feature = "cozo-store")]
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use revet_core::graph::{Edge, EdgeKind, EdgeMetadata, Node, NodeData, NodeKind};
+use revet_core::store::{reconstruct_graph, CozoStore, GraphStore, Retention};
+use revet_core::CodeGraph;
+use tempfile::TempDir;
+
+/// A graph of `n` functions in files of 50. Every 100th function's return
+/// type changes with `version`, so consecutive versions differ in 1% of
+/// their nodes.
+fn graph(n: usize, version: usize) -> CodeGraph {
+    let mut graph = CodeGraph::new(PathBuf::from("/repo"));
+    let ids: Vec<_> = (0..n)
+        .map(|i| {
+            let revision = if i % 100 == 0 { version } else { 0 };
+            graph.add_node(Node::new(
+                NodeKind::Function,
+                format!("func_{i}"),
+                PathBuf::from(format!("src/module_{}.py", i / 50)),
+                (i % 50) * 10 + 1,
+                NodeData::Function {
+                    parameters: vec![],
+                    return_type: Some(format!("Result{revision}")),
+                },
+            ))
+        })
+        .collect();
+    for i in 0..n {
+        graph.add_edge(
+            ids[i],
+            ids[(i + 1) % n],
+            Edge::with_metadata(
+                EdgeKind::Calls,
+                EdgeMetadata::Call {
+                    line: (i % 50) * 10 + 2,
+                    is_direct: true,
+                },
+            ),
+        );
+        graph.add_edge(ids[i], ids[i / 2], Edge::new(EdgeKind::Imports));
+    }
+    graph.assign_fingerprints();
+    graph
+}
+
+/// Versions that also add and remove nodes (and the edges around them)
+fn versions() -> Vec<CodeGraph> {
+    (0..12).map(|v| graph(300 + (v * 7) % 11, v)).collect()
+}
+
+/// Every node and edge of `graph`, in graph order
+fn canonical(graph: &CodeGraph) -> Vec<u8> {
+    let nodes: Vec<&Node> = graph.nodes().map(|(_, node)| node).collect();
+    let edges: Vec<(usize, usize, &Edge)> = graph
+        .nodes()
+        .flat_map(|(id, _)| {
+            graph
+                .edges_from(id)
+                .map(move |(target, edge)| (id.index(), target.index(), edge))
+        })
+        .collect();
+    serde_json::to_vec(&(nodes, edges)).unwrap()
+}
+
+/// Nodes in graph order and edges sorted, independent of edge insertion order
+fn content(graph: &CodeGraph) -> String {
+    let mut edges: Vec<String> = graph
+        .nodes()
+        .flat_map(|(id, _)| {
+            graph
+                .edges_from(id)
+                .map(move |(target, edge)| format!("{}->{} {:?}", id.index(), target.index(), edge))
+        })
+        .collect();
+    edges.sort();
+    let nodes: Vec<String> = graph.nodes().map(|(_, n)| format!("{n:?}")).collect();
+    format!("{nodes:?}\n{edges:?}")
+}
+
+fn reconstruct(store: &CozoStore, snapshot: &str) -> CodeGraph {
+    reconstruct_graph(store, snapshot, Path::new("/repo")).unwrap()
+}
+
+fn name(v: usize) -> String {
+    format!("s{v:02}")
+}
+
+// ── Delta chains ─────────────────────────────────────────────────
+
+#[test]
+fn test_delta_chain_reconstructs_identically_to_full_snapshots() {
+    let versions = versions();
+    let deltas = CozoStore::new_memory().unwrap();
+    let full = CozoStore::new_memory().unwrap().with_full_every(1);
+    for (v, graph) in versions.iter().enumerate() {
+        deltas.flush(graph, &name(v)).unwrap();
+        full.flush(graph, &name(v)).unwrap();
+    }
+
+    for (v, graph) in versions.iter().enumerate() {
+        let from_deltas = reconstruct(&deltas, &name(v));
+        let from_full = reconstruct(&full, &name(v));
+        assert_eq!(
+            canonical(&from_deltas),
+            canonical(&from_full),
+            "snapshot {v}"
+        );
+        assert_eq!(content(&from_full), content(graph), "snapshot {v}");
+    }
+}
+
+#[test]
+fn test_every_nth_snapshot_is_full() {
+    let store = CozoStore::new_memory().unwrap().with_full_every(4);
+    for (v, graph) in versions().iter().enumerate() {
+        store.flush(graph, &name(v)).unwrap();
+    }
+    for v in 0..12 {
+        let expected = (v % 4 != 0).then(|| name(v - 1));
+        assert_eq!(store.base(&name(v)).unwrap(), expected, "snapshot {v}");
+    }
+}
+
+#[test]
+fn test_unchanged_graph_stores_an_empty_delta() {
+    let store = CozoStore::new_memory().unwrap();
+    let graph = graph(300, 0);
+    store.flush(&graph, "a").unwrap();
+    store.flush(&graph, "b").unwrap();
+    assert_eq!(store.base("b").unwrap().as_deref(), Some("a"));
+    assert_eq!(store.node_count("b").unwrap(), 300);
+    assert_eq!(
+        canonical(&reconstruct(&store, "b")),
+        canonical(&reconstruct(&store, "a"))
+    );
+}
+
+#[test]
+fn test_removed_nodes_and_edges_are_gone_from_the_delta() {
+    let store = CozoStore::new_memory().unwrap();
+    store.flush(&graph(310, 0), "a").unwrap();
+    store.flush(&graph(300, 0), "b").unwrap();
+    assert_eq!(store.node_count("b").unwrap(), 300);
+    assert!(store
+        .node(revet_core::StoreNodeId(305), "b")
+        .unwrap()
+        .is_none());
+    assert!(store
+        .node(revet_core::StoreNodeId(305), "a")
+        .unwrap()
+        .is_some());
+    assert_eq!(content(&reconstruct(&store, "b")), content(&graph(300, 0)));
+}
+
+#[test]
+fn test_deleting_a_base_keeps_its_deltas() {
+    let versions = versions();
+    let store = CozoStore::new_memory().unwrap();
+    for (v, graph) in versions.iter().enumerate().take(4) {
+        store.flush(graph, &name(v)).unwrap();
+    }
+    let before: Vec<Vec<u8>> = (1..4)
+        .map(|v| canonical(&reconstruct(&store, &name(v))))
+        .collect();
+
+    store.delete_snapshot(&name(0)).unwrap();
+    store.delete_snapshot(&name(2)).unwrap();
+
+    assert_eq!(store.base(&name(1)).unwrap(), None);
+    assert_eq!(store.base(&name(3)).unwrap(), Some(name(1)));
+    assert_eq!(canonical(&reconstruct(&store, &name(1))), before[0]);
+    assert_eq!(canonical(&reconstruct(&store, &name(3))), before[2]);
+}
+
+#[test]
+fn test_flushing_an_existing_name_replaces_it() {
+    let store = CozoStore::new_memory().unwrap();
+    store.flush(&graph(300, 0), "cached").unwrap();
+    store.flush(&graph(300, 1), "cached").unwrap();
+    let snapshots = store.snapshots().unwrap();
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(store.base("cached").unwrap(), None);
+    assert_eq!(
+        content(&reconstruct(&store, "cached")),
+        content(&graph(300, 1))
+    );
+}
+
+// ── gc ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_gc_keep_last() {
+    let store = CozoStore::new_memory().unwrap();
+    for (v, graph) in versions().iter().enumerate() {
+        store.flush(graph, &name(v)).unwrap();
+    }
+    let before: Vec<Vec<u8>> = (9..12)
+        .map(|v| canonical(&reconstruct(&store, &name(v))))
+        .collect();
+
+    let retention = Retention {
+        keep_last: Some(3),
+        keep_days: None,
+    };
+    let report = store.gc(&retention, SystemTime::now()).unwrap();
+    assert_eq!(report.removed, (0..9).map(name).collect::<Vec<_>>());
+    assert_eq!(report.kept, 3);
+
+    let mut left: Vec<String> = store
+        .snapshots()
+        .unwrap()
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    left.sort();
+    assert_eq!(left, vec![name(9), name(10), name(11)]);
+    for (i, v) in (9..12).enumerate() {
+        assert_eq!(
+            canonical(&reconstruct(&store, &name(v))),
+            before[i],
+            "snapshot {v}"
+        );
+    }
+}
+
+#[test]
+fn test_gc_keep_days() {
+    let store = CozoStore::new_memory().unwrap();
+    for (v, graph) in versions().iter().enumerate().take(3) {
+        store.flush(graph, &name(v)).unwrap();
+    }
+    let later = SystemTime::now() + Duration::from_secs(10 * 86_400);
+
+    let recent = Retention {
+        keep_last: None,
+        keep_days: Some(30),
+    };
+    assert!(store.gc(&recent, later).unwrap().removed.is_empty());
+
+    let week = Retention {
+        keep_last: None,
+        keep_days: Some(7),
+    };
+    let report = store.gc(&week, later).unwrap();
+    assert_eq!(report.removed, vec![name(0), name(1)]);
+    assert_eq!(report.kept, 1);
+    assert_eq!(
+        content(&reconstruct(&store, &name(2))),
+        content(&versions()[2])
+    );
+}
+
+#[test]
+fn test_gc_without_retention_keeps_everything() {
+    let store = CozoStore::new_memory().unwrap();
+    for (v, graph) in versions().iter().enumerate().take(3) {
+        store.flush(graph, &name(v)).unwrap();
+    }
+    let report = store.gc(&Retention::default(), SystemTime::now()).unwrap();
+    assert!(report.removed.is_empty());
+    assert!(report.compacted.is_empty());
+    assert_eq!(report.kept, 3);
+}
+
+#[test]
+fn test_gc_compacts_chains_longer_than_full_every() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("graph.db");
+    let versions = versions();
+    {
+        let store = CozoStore::new_sqlite(&path).unwrap();
+        for (v, graph) in versions.iter().enumerate().take(5) {
+            store.flush(graph, &name(v)).unwrap();
+        }
+    }
+
+    let store = CozoStore::new_sqlite(&path).unwrap().with_full_every(2);
+    let report = store.gc(&Retention::default(), SystemTime::now()).unwrap();
+    // Oldest first: once s02 is full, s03 is a short delta of it again
+    assert_eq!(report.compacted, vec![name(2), name(4)]);
+    assert_eq!(store.base(&name(1)).unwrap(), Some(name(0)));
+    assert_eq!(store.base(&name(2)).unwrap(), None);
+    assert_eq!(store.base(&name(3)).unwrap(), Some(name(2)));
+    assert_eq!(store.base(&name(4)).unwrap(), None);
+    for (v, graph) in versions.iter().enumerate().take(5) {
+        assert_eq!(
+            content(&reconstruct(&store, &name(v))),
+            content(graph),
+            "snapshot {v}"
+        );
+    }
+}
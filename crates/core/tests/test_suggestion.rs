@@ -0,0 +1,170 @@
+//! Integration tests for parse-checking code suggestions
+
+use revet_core::finding::{Finding, InsertContext, SuggestedCode};
+use revet_core::suggestion::{self, SuggestionStats};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn code(path: &str, snippet: &str, insert_context: InsertContext) -> SuggestedCode {
+    suggestion::code(Path::new(path), snippet, insert_context).unwrap()
+}
+
+/// A finding on `line` of `file` carrying `code`
+fn finding(file: &str, line: usize, rule: &str, code: SuggestedCode) -> Finding {
+    Finding {
+        id: "SQL-001".to_string(),
+        rule: Some(rule.to_string()),
+        message: "SQL built by interpolation".to_string(),
+        file: PathBuf::from(file),
+        line,
+        suggestion: Some("Use a parameterized query".to_string()),
+        suggestion_code: Some(code),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_code_needs_a_known_grammar() {
+    assert!(suggestion::code(Path::new("notes.txt"), "x", InsertContext::Line).is_none());
+    let tsx = code("App.tsx", "x", InsertContext::Line);
+    assert_eq!(tsx.language, "tsx");
+}
+
+#[test]
+fn test_substitute_line_keeps_indentation() {
+    let content = "def f():\n    a = 1\n    return a\n";
+    let (substituted, range) = suggestion::substitute(
+        content,
+        2,
+        &code("a.py", "a = 2\nb = 3", InsertContext::Line),
+    )
+    .unwrap();
+    assert_eq!(
+        substituted,
+        "def f():\n    a = 2\n    b = 3\n    return a\n"
+    );
+    assert_eq!(&substituted[range], "    a = 2\n    b = 3");
+}
+
+#[test]
+fn test_substitute_replace_spans_lines() {
+    let content = "x();\nuseEffect(() => {\n  load();\n});\ny();\n";
+    let replace = InsertContext::Replace {
+        original: "useEffect(() => {\n  load();\n})".to_string(),
+    };
+    let (substituted, range) = suggestion::substitute(
+        content,
+        2,
+        &code("a.js", "useEffect(() => {\n  load();\n}, [])", replace),
+    )
+    .unwrap();
+    assert_eq!(
+        substituted,
+        "x();\nuseEffect(() => {\n  load();\n}, []);\ny();\n"
+    );
+    assert_eq!(&substituted[range], "useEffect(() => {\n  load();\n}, []);");
+    // The original text must follow the line
+    let missing = InsertContext::Replace {
+        original: "useMemo(".to_string(),
+    };
+    assert!(suggestion::substitute(content, 2, &code("a.js", "x", missing)).is_none());
+    assert!(suggestion::substitute(content, 9, &code("a.js", "x", InsertContext::Line)).is_none());
+}
+
+#[test]
+fn test_parses_only_checks_the_covered_lines() {
+    // Line 3 is broken already; a good snippet on line 1 still parses
+    let content = "a = 1\nb = 2\nc = (\n";
+    let path = Path::new("a.py");
+    assert!(suggestion::parses(
+        path,
+        content,
+        1,
+        &code("a.py", "a = f(1)", InsertContext::Line)
+    ));
+    assert!(!suggestion::parses(
+        path,
+        content,
+        1,
+        &code("a.py", "a = f(1))", InsertContext::Line)
+    ));
+}
+
+#[test]
+fn test_verify_demotes_code_that_does_not_parse() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("db.py"),
+        "def load(cursor, uid):\n    cursor.execute(f\"SELECT * FROM t WHERE id = {uid}\")\n",
+    )
+    .unwrap();
+
+    // The second generator dropped the closing paren
+    let mut findings = vec![
+        finding(
+            "db.py",
+            2,
+            "security:sql_injection",
+            code(
+                "db.py",
+                "cursor.execute(\"SELECT * FROM t WHERE id = ?\", (uid,))",
+                InsertContext::Line,
+            ),
+        ),
+        finding(
+            "db.py",
+            2,
+            "security:sql_injection",
+            code(
+                "db.py",
+                "cursor.execute(\"SELECT * FROM t WHERE id = ?\", (uid,)",
+                InsertContext::Line,
+            ),
+        ),
+        finding(
+            "gone.py",
+            1,
+            "security:secret_exposure",
+            code("gone.py", "x = 1", InsertContext::Line),
+        ),
+    ];
+
+    let stats = suggestion::verify(&mut findings, dir.path());
+    assert!(findings[0].suggestion_code.is_some());
+    assert!(findings[1].suggestion_code.is_none());
+    assert_eq!(
+        findings[1].suggestion.as_deref(),
+        Some("Use a parameterized query")
+    );
+    // Code for a file that can't be read is never shown
+    assert!(findings[2].suggestion_code.is_none());
+
+    let mut expected = SuggestionStats::default();
+    expected
+        .checked
+        .insert("security:sql_injection".to_string(), 2);
+    expected
+        .checked
+        .insert("security:secret_exposure".to_string(), 1);
+    expected
+        .demoted
+        .insert("security:sql_injection".to_string(), 1);
+    expected
+        .demoted
+        .insert("security:secret_exposure".to_string(), 1);
+    assert_eq!(stats, expected);
+    assert_eq!(stats.demoted_total(), 2);
+}
+
+#[test]
+fn test_rule_of_falls_back_to_the_id_prefix() {
+    let mut f = finding(
+        "a.py",
+        1,
+        "security:sql_injection",
+        code("a.py", "x", InsertContext::Line),
+    );
+    assert_eq!(suggestion::rule_of(&f), "security:sql_injection");
+    f.rule = None;
+    assert_eq!(suggestion::rule_of(&f), "SQL");
+}
@@ -1,7 +1,8 @@
 use revet_core::finding::{Finding, Severity};
 use revet_core::suppress::{
-    filter_findings_by_inline, filter_findings_by_path_rules, matches_suppression,
-    parse_suppressions,
+    filter_findings_by_inline, filter_findings_by_path_rules, filter_inline, insert_directives,
+    matches_finding, matches_suppression, parse_directives, parse_suppressions,
+    parse_suppressions_at, removal_range, DirectiveDefect,
 };
 use std::io::Write;
 use std::path::PathBuf;
@@ -62,6 +63,221 @@ fn test_parse_dash_in_prefix() {
     assert_eq!(sups.get(&1).unwrap(), &["MY-CUSTOM"]);
 }
 
+#[test]
+fn test_parse_sub_rule_with_colon() {
+    let content = "// revet-ignore: error_handling:swallowed ASYNC
+code()
+";
+    let sups = parse_suppressions(content);
+    assert_eq!(
+        sups.get(&1).unwrap(),
+        &["error_handling:swallowed", "ASYNC"]
+    );
+}
+
+#[test]
+fn test_parse_block_comment_closer_not_a_token() {
+    let content = "x = 1 /* revet-ignore SEC */\n<!-- revet-ignore SEC SQL -->\n";
+    let sups = parse_suppressions(content);
+    assert_eq!(sups.get(&1).unwrap(), &["SEC"]);
+    assert_eq!(sups.get(&2).unwrap(), &["SEC", "SQL"]);
+}
+
+#[test]
+fn test_parse_until_mixed_dates() {
+    let content = "# revet-ignore SEC SQL until=2025-06-01 reason=\"migrating to vault\" ML until=2025-09-30 CMD\n";
+    let on = |today| parse_suppressions_at(content, today).remove(&1);
+
+    // `until` is the last day the rules apply
+    assert_eq!(on("2025-06-01").unwrap(), &["SEC", "SQL", "ML", "CMD"]);
+    assert_eq!(on("2025-06-02").unwrap(), &["ML", "CMD"]);
+    assert_eq!(on("2025-10-01").unwrap(), &["CMD"]);
+
+    // Every rule expired: nothing left on the line
+    let content = "// revet-ignore SEC until=2020-01-01\n";
+    assert!(parse_suppressions_at(content, "2025-01-01").is_empty());
+}
+
+#[test]
+fn test_parse_bad_until_never_expires() {
+    let content = "# revet-ignore SEC until=June reason=vault\n";
+    assert_eq!(
+        parse_suppressions_at(content, "2999-01-01")
+            .get(&1)
+            .unwrap(),
+        &["SEC"]
+    );
+}
+
+// ── parse_directives ───────────────────────────────────────────
+
+#[test]
+fn test_directive_tokens_and_columns() {
+    let directives = parse_directives("key = 'x'  # revet-ignore SEC SQL\n");
+    assert_eq!(directives.len(), 1);
+    let d = &directives[0];
+    assert_eq!((d.line, d.column, d.end_column), (1, 14, 34));
+    assert_eq!(
+        d.tokens,
+        vec![("SEC".to_string(), 27), ("SQL".to_string(), 31)]
+    );
+    assert_eq!(d.defect, None);
+}
+
+#[test]
+fn test_directive_defects() {
+    let defect = |line: &str| parse_directives(line).remove(0).defect;
+
+    assert_eq!(
+        defect("# revet_ignore SEC"),
+        Some(DirectiveDefect::UnknownKeyword("revet_ignore".to_string()))
+    );
+    assert_eq!(
+        defect("/* revet-disable SEC */"),
+        Some(DirectiveDefect::UnknownKeyword("revet-disable".to_string()))
+    );
+    assert_eq!(
+        defect("// revet-ignore:SEC"),
+        Some(DirectiveDefect::MissingSpace("SEC".to_string()))
+    );
+    assert_eq!(defect("// revet-ignore"), Some(DirectiveDefect::NoRules));
+    assert_eq!(defect("/* revet-ignore */"), Some(DirectiveDefect::NoRules));
+    assert_eq!(
+        defect("# revet-ignore SEC, SQL ML"),
+        Some(DirectiveDefect::Separator {
+            separator: ',',
+            ignored: vec!["SQL".to_string(), "ML".to_string()],
+        })
+    );
+    assert_eq!(defect("# revet-ignore: SEC"), None);
+}
+
+#[test]
+fn test_directive_options() {
+    let line =
+        "x = 1  # revet-ignore SEC until=2025-06-01 reason=\"moving to vault\" ML until=2025-07-01";
+    let d = parse_directives(line).remove(0);
+    assert_eq!(d.defect, None);
+    assert_eq!(d.tokens.len(), 2);
+    assert_eq!(d.options.len(), 2);
+
+    let first = d.options_of(0).unwrap();
+    assert_eq!(first.until.as_deref(), Some("2025-06-01"));
+    assert_eq!(first.reason.as_deref(), Some("moving to vault"));
+    assert_eq!((first.column, first.end_column), (27, 68));
+    // No reason is fine
+    let second = d.options_of(1).unwrap();
+    assert_eq!(second.until.as_deref(), Some("2025-07-01"));
+    assert_eq!(second.reason, None);
+    assert_eq!(d.end_column, line.chars().count() + 1);
+
+    assert!(!d.is_expired(0, "2025-06-01"));
+    assert!(d.is_expired(0, "2025-06-15") && !d.is_expired(1, "2025-06-15"));
+}
+
+#[test]
+fn test_directive_option_defects() {
+    let defect = |line: &str| parse_directives(line).remove(0).defect;
+
+    assert_eq!(
+        defect("# revet-ignore SEC until=2025-13-01"),
+        Some(DirectiveDefect::InvalidDate("2025-13-01".to_string()))
+    );
+    assert_eq!(
+        defect("# revet-ignore SEC until=\"next week\""),
+        Some(DirectiveDefect::InvalidDate("next week".to_string()))
+    );
+    assert_eq!(
+        defect("# revet-ignore SEC owner=alice"),
+        Some(DirectiveDefect::UnknownOption("owner".to_string()))
+    );
+    assert_eq!(
+        defect("# revet-ignore until=2025-06-01"),
+        Some(DirectiveDefect::NoRules)
+    );
+}
+
+#[test]
+fn test_directive_in_string_or_code_span_skipped() {
+    let content = "msg = \"revet-ignore SEC\"\n/// Add `revet-ignore SEC` to silence\n";
+    assert!(parse_directives(content).is_empty());
+}
+
+// ── removal_range ──────────────────────────────────────────────
+
+fn remove(line: &str, token: Option<&str>) -> Option<(String, bool)> {
+    let (start, end, removes_line) = removal_range(line, token)?;
+    let chars: Vec<char> = line.chars().collect();
+    let kept: String = chars[..start - 1].iter().chain(&chars[end - 1..]).collect();
+    Some((kept, removes_line))
+}
+
+#[test]
+fn test_removal_range_whole_comment() {
+    assert_eq!(
+        remove("    return 30  # revet-ignore SQL", None),
+        Some(("    return 30".to_string(), false))
+    );
+    assert_eq!(
+        remove("\treturn 10 /* revet-ignore CMD */", None),
+        Some(("\treturn 10".to_string(), false))
+    );
+    assert_eq!(
+        remove("  // revet-ignore SEC", None),
+        Some((String::new(), true))
+    );
+}
+
+#[test]
+fn test_removal_range_single_token() {
+    assert_eq!(
+        remove("key = 1; // revet-ignore SEC SQL", Some("SQL")),
+        Some(("key = 1; // revet-ignore SEC".to_string(), false))
+    );
+    assert_eq!(
+        remove("key = 1 /* revet-ignore SEC SQL */", Some("SEC")),
+        Some(("key = 1 /* revet-ignore SQL */".to_string(), false))
+    );
+    // The only rule takes the comment with it
+    assert_eq!(
+        remove("key = 1  # revet-ignore SEC", Some("SEC")),
+        Some(("key = 1".to_string(), false))
+    );
+}
+
+#[test]
+fn test_removal_range_takes_the_rules_options() {
+    let line = "x = 1  # revet-ignore SEC until=2025-06-01 reason=\"vault\" ML";
+    assert_eq!(
+        remove(line, Some("SEC")),
+        Some(("x = 1  # revet-ignore ML".to_string(), false))
+    );
+    assert_eq!(
+        remove(line, Some("ML")),
+        Some((
+            "x = 1  # revet-ignore SEC until=2025-06-01 reason=\"vault\"".to_string(),
+            false
+        ))
+    );
+    // Options shared with another rule stay
+    assert_eq!(
+        remove("# revet-ignore SEC SQL until=2025-06-01", Some("SEC")),
+        Some(("# revet-ignore SQL until=2025-06-01".to_string(), false))
+    );
+    assert_eq!(
+        remove("x = 1  # revet-ignore SEC until=2025-06-01", Some("SEC")),
+        Some(("x = 1".to_string(), false))
+    );
+}
+
+#[test]
+fn test_removal_range_keeps_other_comment_text() {
+    assert_eq!(
+        remove("x = 1  # legacy revet-ignore SEC", None),
+        Some(("x = 1  # legacy".to_string(), false))
+    );
+}
+
 // ── filter_findings_by_inline ──────────────────────────────────
 
 fn make_finding(id: &str, file: PathBuf, line: usize) -> Finding {
@@ -90,6 +306,27 @@ fn test_same_line_suppression() {
     assert!(kept.is_empty());
 }
 
+#[test]
+fn test_sub_rule_suppression() {
+    let mut f = NamedTempFile::new().unwrap();
+    writeln!(f, "// revet-ignore: error_handling:swallowed").unwrap();
+    writeln!(f, "}} catch (e) {{ console.log(e); }}").unwrap();
+    let path = f.path().to_path_buf();
+
+    let tagged = |id: &str, rule: &str| Finding {
+        rule: Some(rule.to_string()),
+        ..make_finding(id, path.clone(), 2)
+    };
+    let findings = vec![
+        tagged("ERR-001", "error_handling:swallowed"),
+        tagged("ERR-002", "error_handling:empty_catch"),
+    ];
+    let (kept, suppressed) = filter_findings_by_inline(findings);
+    assert_eq!(suppressed.len(), 1);
+    assert_eq!(suppressed[0].finding.id, "ERR-001");
+    assert_eq!(kept[0].id, "ERR-002");
+}
+
 #[test]
 fn test_line_before_suppression() {
     let mut f = NamedTempFile::new().unwrap();
@@ -309,3 +546,81 @@ fn test_matches_suppression_multiple_prefixes() {
     assert!(matches_suppression("SQL-001", &prefixes));
     assert!(!matches_suppression("ML-001", &prefixes));
 }
+
+#[test]
+fn test_matches_finding_module_and_sub_rule() {
+    let finding = Finding {
+        rule: Some("error_handling:unwrap".to_string()),
+        ..make_finding("ERR-003", PathBuf::from("lib.rs"), 1)
+    };
+    let tokens = |t: &[&str]| t.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert!(matches_finding(&finding, &tokens(&["ERR"])));
+    assert!(matches_finding(&finding, &tokens(&["error_handling"])));
+    assert!(matches_finding(
+        &finding,
+        &tokens(&["error_handling:unwrap"])
+    ));
+    assert!(!matches_finding(
+        &finding,
+        &tokens(&["error_handling:swallowed"])
+    ));
+    assert!(!matches_finding(&finding, &tokens(&["unwrap"])));
+}
+
+// ── filter_inline usage ──────────────────────────────────────
+
+#[test]
+fn test_filter_inline_records_used_tokens() {
+    let mut tmp = NamedTempFile::new().unwrap();
+    writeln!(tmp, "# revet-ignore SEC SQL").unwrap();
+    writeln!(tmp, "key = 'AKIA...'").unwrap();
+    writeln!(tmp, "q = run(sql)  # revet-ignore ML").unwrap();
+    let path = tmp.path().to_path_buf();
+
+    let outcome = filter_inline(vec![
+        make_finding("SEC-001", path.clone(), 2),
+        make_finding("SQL-001", path.clone(), 3),
+    ]);
+    assert_eq!(outcome.suppressed.len(), 1);
+    assert_eq!(outcome.kept.len(), 1);
+    assert!(outcome.usage.is_used(&path, 1, "SEC"));
+    assert!(!outcome.usage.is_used(&path, 1, "SQL"));
+    assert!(!outcome.usage.is_used(&path, 3, "ML"));
+}
+
+// ── insert_directives ────────────────────────────────────────
+
+#[test]
+fn test_insert_directives_with_reasons() {
+    let content = "def run():\n    key = 'AKIA...'\n    q = run(sql)\n";
+    let updated = insert_directives(
+        std::path::Path::new("app.py"),
+        content,
+        &[
+            (
+                3,
+                "security:sql_injection".to_string(),
+                "escaped upstream".to_string(),
+            ),
+            (2, "security:secrets".to_string(), "test key".to_string()),
+            (3, "SQL".to_string(), "checked".to_string()),
+        ],
+    );
+    assert_eq!(
+        updated,
+        "def run():\n    # revet-ignore security:secrets — test key\n    key = 'AKIA...'\n    # revet-ignore security:sql_injection SQL — escaped upstream; checked\n    q = run(sql)\n"
+    );
+
+    // The reason after the dash is not read as rules
+    let sups = parse_suppressions(&updated);
+    assert_eq!(sups.get(&2).unwrap(), &["security:secrets"]);
+    assert_eq!(sups.get(&4).unwrap(), &["security:sql_injection", "SQL"]);
+
+    let rust = insert_directives(
+        std::path::Path::new("lib.rs"),
+        "fn a() {}",
+        &[(1, "SEC".to_string(), "fixture".to_string())],
+    );
+    assert_eq!(rust, "// revet-ignore SEC — fixture\nfn a() {}");
+}
@@ -105,7 +105,7 @@ class Dog: Animal, Runnable {
     } = dog.1.data()
     {
         assert!(
-            base_classes.len() >= 1,
+            !base_classes.is_empty(),
             "Expected at least Animal in base_classes, got {:?}",
             base_classes
         );
@@ -566,7 +566,7 @@ class Service {
         .collect();
 
     assert!(
-        process_calls.len() >= 1,
+        !process_calls.is_empty(),
         "process should call at least one method, got {}",
         process_calls.len()
     );
@@ -2,9 +2,9 @@ use revet_core::analyzer::test_coverage::TestCoverageAnalyzer;
 use revet_core::analyzer::GraphAnalyzer;
 use revet_core::config::RevetConfig;
 use revet_core::graph::{CodeGraph, Edge, EdgeKind, Node, NodeData, NodeKind};
-use std::io::Write;
+use revet_core::CodeKind;
 use std::path::PathBuf;
-use tempfile::{tempdir, NamedTempFile};
+use tempfile::tempdir;
 
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
@@ -22,6 +22,7 @@ fn add_file_node(graph: &mut CodeGraph, path: &str) -> revet_core::graph::NodeId
         0,
         NodeData::File {
             language: "rust".to_string(),
+            code_kind: CodeKind::Production,
         },
     ))
 }
@@ -202,7 +203,7 @@ fn test_class_without_tests_flagged() {
 
 #[test]
 fn test_disabled_by_default() {
-    let mut graph = CodeGraph::new(PathBuf::from("/tmp"));
+    let graph = CodeGraph::new(PathBuf::from("/tmp"));
     let config = RevetConfig::default(); // test_coverage = false
     let analyzer = TestCoverageAnalyzer::new();
     assert!(!analyzer.is_enabled(&config));
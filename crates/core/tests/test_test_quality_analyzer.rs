@@ -0,0 +1,542 @@
+//! Integration tests for TestQualityAnalyzer
+
+mod common;
+
+use git2::{Repository, Signature, Time};
+use revet_core::analyzer::test_quality::TestQualityAnalyzer;
+use revet_core::analyzer::Analyzer;
+use revet_core::config::{FilesConfig, RevetConfig, TestQualityConfig};
+use revet_core::finding::{Confidence, Finding, Severity};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tempfile::TempDir;
+
+const FIXTURE: &str = "test_quality";
+const EXTENSIONS: &[&str] = &[
+    ".py", ".ts", ".js", ".java", ".kt", ".go", ".rs", ".rb", ".cs", ".php",
+];
+
+/// (repo-relative file, line, sub-rule) for each TEST finding, sorted
+fn summary(findings: &[Finding], root: &Path) -> Vec<(String, usize, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("TEST"))
+        .map(|f| {
+            let file = f.file.strip_prefix(root).unwrap_or(&f.file);
+            (
+                file.to_string_lossy().replace('\\', "/"),
+                f.line,
+                f.rule.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+fn row(file: &str, line: usize, rule: &str) -> (String, usize, String) {
+    (file.to_string(), line, format!("test_quality:{}", rule))
+}
+
+fn analyze(file: &str, content: &str) -> Vec<Finding> {
+    analyze_with(&TestQualityConfig::default(), file, content)
+}
+
+fn analyze_with(config: &TestQualityConfig, file: &str, content: &str) -> Vec<Finding> {
+    TestQualityAnalyzer::with_config(config).analyze_source(
+        Path::new(file),
+        content,
+        Path::new("."),
+    )
+}
+
+/// (line, sub-rule) pairs
+fn lines(findings: &[Finding]) -> Vec<(usize, String)> {
+    findings
+        .iter()
+        .map(|f| (f.line, f.rule.clone().unwrap_or_default()))
+        .collect()
+}
+
+fn at(line: usize, rule: &str) -> (usize, String) {
+    (line, rule.to_string())
+}
+
+// ── Fixture ──────────────────────────────────────────────────────
+
+#[test]
+fn test_fixture() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    assert_eq!(
+        summary(&findings, &root),
+        vec![
+            row("cypress/checkout.cy.js", 4, "sleep"),
+            row("cypress/checkout.cy.js", 12, "sleep"),
+            row("golang/orders_test.go", 11, "sleep"),
+            row("golang/orders_test.go", 17, "no_assertions"),
+            row("jest/cart.test.ts", 7, "sleep"),
+            row("jest/cart.test.ts", 11, "no_assertions"),
+            row("jest/cart.test.ts", 16, "broad_exception"),
+            row("junit/OrderServiceTest.java", 16, "sleep"),
+            row("junit/OrderServiceTest.java", 21, "no_assertions"),
+            row("junit/OrderServiceTest.java", 27, "broad_exception"),
+            row("kotlin/OrderTest.kt", 10, "broad_exception"),
+            row("phpunit/OrderTest.php", 10, "sleep"),
+            row("phpunit/OrderTest.php", 16, "broad_exception"),
+            row("pytest/test_orders.py", 11, "sleep"),
+            row("pytest/test_orders.py", 15, "no_assertions"),
+            row("pytest/test_orders.py", 20, "broad_exception"),
+            row("rspec/order_spec.rb", 6, "sleep"),
+            row("rspec/order_spec.rb", 10, "no_assertions"),
+            row("rspec/order_spec.rb", 15, "broad_exception"),
+            row("rust/tests/orders.rs", 9, "sleep"),
+            row("rust/tests/orders.rs", 14, "no_assertions"),
+            row("rust/tests/orders.rs", 19, "broad_exception"),
+            row("unittest/test_accounts.py", 13, "broad_exception"),
+            row("unittest/test_accounts.py", 16, "no_assertions"),
+            row("xunit/OrderTests.cs", 11, "sleep"),
+            row("xunit/OrderTests.cs", 18, "broad_exception"),
+        ]
+    );
+}
+
+#[test]
+fn test_fixture_messages_severities_and_confidence() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let at = |file: &str, line: usize| {
+        findings
+            .iter()
+            .find(|f| f.file == root.join(file) && f.line == line)
+            .unwrap_or_else(|| panic!("no finding at {}:{}", file, line))
+    };
+
+    let sleep = at("cypress/checkout.cy.js", 12);
+    assert_eq!(
+        sleep.message,
+        "Fixed wait `page.waitForTimeout` in test code"
+    );
+    assert_eq!(sleep.severity, Severity::Warning);
+    assert_eq!(sleep.confidence, Confidence::High);
+    assert_eq!(
+        at("jest/cart.test.ts", 7).message,
+        "Fixed wait `setTimeout` in test code"
+    );
+
+    let empty = at("rspec/order_spec.rb", 10);
+    assert_eq!(
+        empty.message,
+        "Test `places without checking` asserts nothing"
+    );
+    assert_eq!(empty.confidence, Confidence::Medium);
+    assert!(empty
+        .suggestion
+        .as_deref()
+        .unwrap()
+        .contains("assertion_patterns"));
+
+    assert_eq!(
+        at("pytest/test_orders.py", 20).message,
+        "`with pytest.raises(Exception):` passes for any exception"
+    );
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let analyzer = TestQualityAnalyzer::new();
+    assert!(!analyzer.is_enabled(&RevetConfig::default()));
+    let mut config = RevetConfig::default();
+    config.modules.test_quality = true;
+    assert!(analyzer.is_enabled(&config));
+}
+
+// ── Scope ────────────────────────────────────────────────────────
+
+#[test]
+fn test_only_test_code_is_scanned() {
+    let content = "import time\n\ndef test_poll():\n    time.sleep(1)\n";
+    assert!(analyze("app/poll.py", content).is_empty());
+    assert_eq!(
+        lines(&analyze("app/test_poll.py", content)),
+        vec![at(3, "no_assertions"), at(4, "sleep")]
+    );
+
+    // A production name with test content counts as test code
+    let pytest = format!(
+        "import pytest\n\n{}",
+        content.replacen("import time\n\n", "", 1)
+    );
+    assert_eq!(
+        lines(&analyze("app/poll.py", &pytest)),
+        vec![at(3, "no_assertions"), at(4, "sleep")]
+    );
+}
+
+#[test]
+fn test_test_paths_decide_what_is_test_code() {
+    let files: FilesConfig = toml::from_str("test_paths = [\"qa/**\"]\n").unwrap();
+    let analyzer =
+        TestQualityAnalyzer::with_config(&TestQualityConfig::default()).with_files(&files);
+    let findings = analyzer.analyze_source(
+        Path::new("qa/smoke.py"),
+        "import time\ntime.sleep(3)\n",
+        Path::new("."),
+    );
+    assert_eq!(lines(&findings), vec![at(2, "sleep")]);
+}
+
+// ── Sleeps ───────────────────────────────────────────────────────
+
+#[test]
+fn test_sleeps_per_language() {
+    let cases = [
+        ("test_a.py", "import time\ntime.sleep(0.5)\n", 2),
+        (
+            "a.test.js",
+            "it(\"x\", async () => {\n  await sleep(200);\n  expect(1).toBe(1);\n});\n",
+            2,
+        ),
+        (
+            "FooTest.java",
+            "class FooTest {\n  void f() { TimeUnit.SECONDS.sleep(1); }\n}\n",
+            2,
+        ),
+        (
+            "a_test.go",
+            "package a\n\nfunc helper() {\n\ttime.Sleep(time.Second)\n}\n",
+            4,
+        ),
+        (
+            "tests/a.rs",
+            "fn helper() {\n    tokio::time::sleep(Duration::from_secs(1)).await;\n}\n",
+            2,
+        ),
+        ("a_spec.rb", "def wait\n  sleep(0.1)\nend\n", 2),
+        (
+            "FooTests.cs",
+            "class FooTests {\n  async Task F() { await Task.Delay(250); }\n}\n",
+            2,
+        ),
+        ("FooTest.swift", "func helper() {\n    sleep(2)\n}\n", 2),
+    ];
+    for (file, content, line) in cases {
+        assert_eq!(
+            lines(&analyze(file, content)),
+            vec![at(line, "sleep")],
+            "{}",
+            file
+        );
+    }
+}
+
+#[test]
+fn test_waits_that_are_not_fixed() {
+    let cases = [
+        ("test_a.py", "import asyncio\nawait asyncio.sleep(0)\n"),
+        ("a.cy.js", "cy.wait(\"@orders\");\n"),
+        (
+            "a_test.go",
+            "package a\n\n// time.Sleep(time.Second) is too slow here\n",
+        ),
+        ("test_a.py", "# time.sleep(5)\n"),
+        ("a.test.ts", "jest.advanceTimersByTime(1000);\n"),
+    ];
+    for (file, content) in cases {
+        assert!(analyze(file, content).is_empty(), "{}", file);
+    }
+}
+
+// ── Assertions ───────────────────────────────────────────────────
+
+#[test]
+fn test_assertion_styles() {
+    let asserting = [
+        ("test_a.py", "def test_a():\n    assert f()\n"),
+        ("test_a.py", "def test_a(self):\n    self.assertTrue(f())\n"),
+        ("test_a.py", "def test_a():\n    with pytest.raises(ValueError):\n        f()\n"),
+        ("a.test.js", "test(\"a\", () => expect(f()).toBe(1));\n"),
+        ("a.test.js", "test(\"a\", () => {\n  assert.equal(f(), 1);\n});\n"),
+        ("a.test.js", "it(\"a\", () => {\n  cy.get(\"a\").should(\"exist\");\n});\n"),
+        ("FooTest.java", "class FooTest {\n  @Test\n  void a() { assertThat(f()).isEqualTo(1); }\n}\n"),
+        ("FooTest.java", "class FooTest {\n  @Test\n  void a() { verify(mock).call(); }\n}\n"),
+        ("FooTest.java", "class FooTest {\n  @Test(expected = IllegalStateException.class)\n  void a() { f(); }\n}\n"),
+        ("a_test.go", "package a\n\nfunc TestA(t *testing.T) {\n\tif f() != 1 {\n\t\tt.Fatalf(\"bad\")\n\t}\n}\n"),
+        ("a_test.go", "package a\n\nfunc TestA(t *testing.T) {\n\trequire.NoError(t, f())\n}\n"),
+        ("a_test.go", "package a\n\nfunc TestA(t *testing.T) {\n\tt.Run(\"x\", func(t *testing.T) {\n\t\tassert.Equal(t, 1, f())\n\t})\n}\n"),
+        ("tests/a.rs", "#[test]\nfn a() {\n    assert_eq!(f(), 1);\n}\n"),
+        ("tests/a.rs", "#[test]\nfn a() -> Result<(), E> {\n    f()?;\n    Ok(())\n}\n"),
+        ("tests/a.rs", "#[tokio::test]\nasync fn a() {\n    f().await.unwrap();\n}\n"),
+        ("tests/a.rs", "#[test]\nfn a() {\n    check_roundtrip(1);\n}\n"),
+        ("a_spec.rb", "it \"a\" do\n  expect(f).to eq(1)\nend\n"),
+        ("a_spec.rb", "it \"a\" do\n  f.must_equal 1\nend\n"),
+        ("test_a.rb", "def test_a\n  assert_equal 1, f\nend\n"),
+    ];
+    for (file, content) in asserting {
+        assert!(analyze(file, content).is_empty(), "{}: {}", file, content);
+    }
+}
+
+#[test]
+fn test_tests_without_assertions() {
+    let cases = [
+        ("test_a.py", "def test_a():\n    pass\n", 1, "test_a"),
+        (
+            "a.test.ts",
+            "it(\"saves\", async () => {\n  await save();\n});\n",
+            1,
+            "saves",
+        ),
+        (
+            "FooTest.java",
+            "class FooTest {\n  @ParameterizedTest\n  void checkout() { cart.checkout(); }\n}\n",
+            3,
+            "checkout",
+        ),
+        (
+            "a_test.go",
+            "package a\n\nfunc TestA(t *testing.T) {\n\tf()\n}\n",
+            3,
+            "TestA",
+        ),
+        (
+            "tests/a.rs",
+            "#[test]\nfn a() {\n    let _ = f();\n}\n",
+            2,
+            "a",
+        ),
+        ("test_a.rb", "def test_a\n  f\nend\n", 1, "test_a"),
+    ];
+    for (file, content, line, name) in cases {
+        let findings = analyze(file, content);
+        assert_eq!(
+            lines(&findings),
+            vec![at(line, "no_assertions")],
+            "{}",
+            file
+        );
+        assert_eq!(
+            findings[0].message,
+            format!("Test `{}` asserts nothing", name)
+        );
+    }
+}
+
+#[test]
+fn test_assertion_patterns_name_helpers() {
+    let content = "def test_a():\n    ensure_saved(f())\n";
+    assert_eq!(
+        lines(&analyze("test_a.py", content)),
+        vec![at(1, "no_assertions")]
+    );
+    let config = TestQualityConfig {
+        assertion_patterns: vec!["^ensure_".to_string(), "(unclosed".to_string()],
+        ..TestQualityConfig::default()
+    };
+    assert!(analyze_with(&config, "test_a.py", content).is_empty());
+}
+
+// ── Broad exceptions ─────────────────────────────────────────────
+
+#[test]
+fn test_specific_exceptions_are_fine() {
+    let cases = [
+        (
+            "test_a.py",
+            "with pytest.raises(ExceptionGroup):\n    f()\n",
+        ),
+        ("test_a.py", "self.assertRaises(KeyError, f)\n"),
+        ("a.test.js", "expect(f).toThrow(TypeError);\n"),
+        (
+            "FooTest.java",
+            "assertThrows(IllegalStateException.class, () -> f());\n",
+        ),
+        (
+            "tests/a.rs",
+            "#[should_panic(expected = \"empty\")]\nfn a() {}\n",
+        ),
+        ("a_spec.rb", "expect { f }.to raise_error(KeyError)\n"),
+        (
+            "FooTests.cs",
+            "Assert.Throws<ArgumentException>(() => F());\n",
+        ),
+    ];
+    for (file, content) in cases {
+        assert!(analyze(file, content).is_empty(), "{}", file);
+    }
+}
+
+// ── Stale skips ──────────────────────────────────────────────────
+
+/// Commit `files` to `repo` as authored `days_ago` days ago
+fn commit(repo: &Repository, files: &[(&str, &str)], days_ago: i64) {
+    let root = repo.workdir().unwrap().to_path_buf();
+    let mut index = repo.index().unwrap();
+    for (file, content) in files {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+        index.add_path(Path::new(file)).unwrap();
+    }
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let signature = Signature::new(
+        "Dev",
+        "dev@example.com",
+        &Time::new(now - days_ago * 86_400, 0),
+    )
+    .unwrap();
+    let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+    let parents: Vec<_> = parent.iter().collect();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "change",
+        &tree,
+        &parents,
+    )
+    .unwrap();
+}
+
+const PYTEST_SKIP: &str =
+    "import pytest\n\n@pytest.mark.skip(reason=\"flaky\")\ndef test_a():\n    assert f()\n";
+const JEST_SKIP: &str = "it.skip(\"a\", () => {\n  expect(f()).toBe(1);\n});\n";
+
+#[test]
+fn test_stale_skips_are_dated_by_blame() {
+    let tmp = TempDir::new().unwrap();
+    let repo = Repository::init(tmp.path()).unwrap();
+    commit(&repo, &[("tests/test_old.py", PYTEST_SKIP)], 200);
+    commit(&repo, &[("tests/cart.test.js", JEST_SKIP)], 10);
+    let root = tmp.path().canonicalize().unwrap();
+
+    let analyzer = TestQualityAnalyzer::new();
+    let old = root.join("tests/test_old.py");
+    let findings = analyzer.analyze_files(&[old.clone(), root.join("tests/cart.test.js")], &root);
+    assert_eq!(lines(&findings), vec![at(3, "stale_skip")]);
+    assert_eq!(
+        findings[0].message,
+        "Test disabled by `@pytest.mark.skip(reason=\"flaky\")` for 200 days"
+    );
+    assert_eq!(findings[0].severity, Severity::Info);
+
+    // A shorter allowance makes the recent skip stale too
+    let config = TestQualityConfig {
+        skip_max_age_days: 7,
+        ..TestQualityConfig::default()
+    };
+    let findings = TestQualityAnalyzer::with_config(&config)
+        .analyze_files(&[root.join("tests/cart.test.js")], &root);
+    assert_eq!(lines(&findings), vec![at(1, "stale_skip")]);
+
+    // A skip added since the last commit is not stale, whatever surrounds it
+    let edited = PYTEST_SKIP.replace(
+        "def test_a",
+        "def test_b():\n    assert g()\n\n@pytest.mark.skip\ndef test_a",
+    );
+    let findings = analyzer.analyze_source(&old, &edited, &root);
+    assert_eq!(lines(&findings), vec![at(3, "stale_skip")]);
+}
+
+#[test]
+fn test_skips_outside_a_repository_are_never_stale() {
+    assert!(analyze("test_a.py", PYTEST_SKIP).is_empty());
+}
+
+#[test]
+fn test_only_unconditional_skips_count() {
+    let tmp = TempDir::new().unwrap();
+    let repo = Repository::init(tmp.path()).unwrap();
+    let go = "package a\n\nfunc TestA(t *testing.T) {\n\tt.Skip(\"broken\")\n}\n\nfunc TestB(t *testing.T) {\n\tif short {\n\t\tt.Skip(\"slow\")\n\t}\n\tcheck(t)\n}\n";
+    let ruby = "it \"a\" do\n  skip \"broken\"\nend\n\nit \"b\" do\n  skip \"slow\" if ENV[\"CI\"]\n  expect(1).to eq(1)\nend\n";
+    commit(&repo, &[("a_test.go", go), ("a_spec.rb", ruby)], 400);
+    let root = tmp.path().canonicalize().unwrap();
+    let findings = TestQualityAnalyzer::new()
+        .analyze_files(&[root.join("a_test.go"), root.join("a_spec.rb")], &root);
+    let rows: Vec<_> = findings
+        .iter()
+        .map(|f| {
+            (
+                f.file.file_name().unwrap().to_string_lossy().into_owned(),
+                f.line,
+                f.rule.clone().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            ("a_test.go".to_string(), 4, "stale_skip".to_string()),
+            ("a_spec.rb".to_string(), 2, "stale_skip".to_string()),
+        ]
+    );
+}
+
+// ── Config ───────────────────────────────────────────────────────
+
+#[test]
+fn test_severity_overrides() {
+    let config: TestQualityConfig =
+        toml::from_str("[severity]\nsleep = \"error\"\nno_assertions = \"info\"\n").unwrap();
+    let findings = analyze_with(
+        &config,
+        "test_a.py",
+        "import time\n\ndef test_a():\n    time.sleep(1)\n",
+    );
+    let severities: Vec<Severity> = findings.iter().map(|f| f.severity).collect();
+    assert_eq!(severities, vec![Severity::Info, Severity::Error]);
+}
+
+#[test]
+fn test_sub_rule_selection() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(
+        tmp.path().join("test_a.py"),
+        "import time\n\ndef test_a():\n    time.sleep(1)\n",
+    )
+    .unwrap();
+    let config: RevetConfig = toml::from_str(
+        "[modules]\ntest_quality = true\n\n[analyzers.test_quality]\ndisable = [\"sleep\"]\n",
+    )
+    .unwrap();
+    let findings = common::run(tmp.path(), EXTENSIONS, &config);
+    let rules: Vec<_> = findings.iter().filter_map(|f| f.rule.as_deref()).collect();
+    assert_eq!(rules, vec!["test_quality:no_assertions"]);
+}
+
+#[test]
+fn test_config_validation() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[analyzers.test_quality]
+assertion_patterns = ["^ensure_", "(unclosed"]
+only = ["sleeps"]
+
+[analyzers.test_quality.severity]
+sleep = "fatal"
+flaky = "info"
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    let errors: Vec<&String> = errors
+        .iter()
+        .filter(|e| e.contains("test_quality"))
+        .collect();
+    assert_eq!(errors.len(), 4, "{:?}", errors);
+    assert!(errors.iter().any(|e| e.contains("\"(unclosed\"")));
+    assert!(errors.iter().any(|e| e.contains("\"sleeps\"")));
+    assert!(errors.iter().any(|e| e.contains("sleep = \"fatal\"")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("unknown sub-rule \"flaky\"")));
+
+    assert!(RevetConfig::default()
+        .validate()
+        .0
+        .iter()
+        .all(|e| !e.contains("test_quality")));
+}
@@ -0,0 +1,449 @@
+//! Integration tests for TranslationsAnalyzer
+
+mod common;
+
+use revet_core::analyzer::translations::catalog::CatalogPattern;
+use revet_core::analyzer::translations::TranslationsAnalyzer;
+use revet_core::analyzer::Analyzer;
+use revet_core::config::{I18nConfig, RevetConfig};
+use revet_core::finding::{Finding, Severity};
+use std::path::Path;
+use tempfile::TempDir;
+
+const FIXTURE: &str = "i18n_app";
+const EXTENSIONS: &[&str] = &[".json", ".yaml", ".yml", ".po", ".ts", ".tsx", ".js", ".py"];
+
+/// (repo-relative file, line, sub-rule) for each I18N finding, sorted
+fn summary(findings: &[Finding], root: &Path) -> Vec<(String, usize, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("I18N"))
+        .map(|f| {
+            let file = f.file.strip_prefix(root).unwrap_or(&f.file);
+            (
+                file.to_string_lossy().replace('\\', "/"),
+                f.line,
+                f.rule.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+fn row(file: &str, line: usize, rule: &str) -> (String, usize, String) {
+    (file.to_string(), line, rule.to_string())
+}
+
+fn write(root: &Path, rel: &str, content: &str) {
+    let path = root.join(rel);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+fn analyze(root: &Path) -> Vec<Finding> {
+    analyze_with(&I18nConfig::default(), root)
+}
+
+fn analyze_with(config: &I18nConfig, root: &Path) -> Vec<Finding> {
+    TranslationsAnalyzer::with_config(config)
+        .analyze_files(&common::discover(root, EXTENSIONS), root)
+}
+
+fn i18n(toml: &str) -> I18nConfig {
+    let config: RevetConfig = toml::from_str(toml).unwrap();
+    config.analyzers.i18n
+}
+
+/// (sub-rule, message) pairs, sorted
+fn messages(findings: &[Finding]) -> Vec<(String, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .map(|f| (f.rule.clone().unwrap_or_default(), f.message.clone()))
+        .collect();
+    rows.sort();
+    rows
+}
+
+// ── Fixture app ──────────────────────────────────────────────────
+
+#[test]
+fn test_fixture_app() {
+    let root = common::fixture_root(FIXTURE);
+    let config = RevetConfig::from_file(&root.join(".revet.toml")).unwrap();
+    let findings = common::run(&root, EXTENSIONS, &config);
+
+    assert_eq!(
+        summary(&findings, &root),
+        vec![
+            row("src/components/Checkout.tsx", 9, "i18n:missing_key"),
+            row("src/locales/en/common.json", 13, "i18n:unused_key"),
+        ]
+    );
+}
+
+#[test]
+fn test_fixture_messages_and_severities() {
+    let root = common::fixture_root(FIXTURE);
+    let config = RevetConfig::from_file(&root.join(".revet.toml")).unwrap();
+    let findings = common::run(&root, EXTENSIONS, &config);
+
+    let missing = findings
+        .iter()
+        .find(|f| f.rule.as_deref() == Some("i18n:missing_key"))
+        .unwrap();
+    assert_eq!(
+        missing.message,
+        "Translation key `checkout.pay` is missing from fr"
+    );
+    assert_eq!(missing.severity, Severity::Warning);
+
+    let unused = findings
+        .iter()
+        .find(|f| f.rule.as_deref() == Some("i18n:unused_key"))
+        .unwrap();
+    assert_eq!(
+        unused.message,
+        "Translation key `promo.holiday_banner` is never looked up (en, fr)"
+    );
+    assert_eq!(unused.severity, Severity::Info);
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let root = common::fixture_root(FIXTURE);
+    let findings = common::run(&root, EXTENSIONS, &RevetConfig::default());
+    assert!(findings.iter().all(|f| !f.id.starts_with("I18N")));
+}
+
+// ── Lookups ──────────────────────────────────────────────────────
+
+#[test]
+fn test_dynamic_keys_are_reported_and_cover_their_prefix() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "locales/en.json",
+        r#"{"errors": {"timeout": "Timed out", "offline": "Offline"}, "stale": "Old"}"#,
+    );
+    write(
+        dir.path(),
+        "app.ts",
+        "export const a = (code: string) => t(`errors.${code}`);\nexport const b = (key: string) => t(key);\n",
+    );
+
+    let findings = analyze(dir.path());
+    assert_eq!(
+        messages(&findings),
+        vec![
+            (
+                "dynamic_key".to_string(),
+                "Translation key ``errors.${code}`` is built at runtime and cannot be checked against the catalogs"
+                    .to_string()
+            ),
+            (
+                "dynamic_key".to_string(),
+                "Translation key `key` is built at runtime and cannot be checked against the catalogs"
+                    .to_string()
+            ),
+            (
+                "unused_key".to_string(),
+                "Translation key `stale` is never looked up (en)".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_concatenated_key_prefix() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "locales/en.json",
+        r#"{"status": {"open": "Open", "closed": "Closed"}}"#,
+    );
+    write(
+        dir.path(),
+        "status.js",
+        "export const label = (s) => i18n.t('status.' + s);\n",
+    );
+
+    let findings = analyze(dir.path());
+    assert_eq!(findings.len(), 1, "{:?}", messages(&findings));
+    assert_eq!(findings[0].rule.as_deref(), Some("dynamic_key"));
+}
+
+#[test]
+fn test_receivers_and_vue_lookups() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "locales/en.json",
+        r#"{"a": "A", "b": "B", "c": "C"}"#,
+    );
+    write(dir.path(), "locales/de.json", r#"{}"#);
+    write(
+        dir.path(),
+        "component.ts",
+        "class C {\n  x() { return this.translate.instant('a'); }\n  y() { return this.$t('b'); }\n  z() { return format('c'); }\n}\n",
+    );
+
+    let findings = analyze(dir.path());
+    let rows: Vec<_> = findings
+        .iter()
+        .map(|f| (f.rule.clone().unwrap(), f.line))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            ("missing_key".to_string(), 2),
+            ("missing_key".to_string(), 3),
+            ("unused_key".to_string(), 1),
+        ]
+    );
+}
+
+#[test]
+fn test_extra_functions_and_framework_selection() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "locales/en.json", r#"{"a": "A"}"#);
+    write(dir.path(), "locales/fr.json", r#"{}"#);
+    write(dir.path(), "app.js", "tr('a');\nt('a');\n");
+
+    let config = i18n("[analyzers.i18n]\nframeworks = [\"gettext\"]\nfunctions = [\"tr\"]");
+    let findings = analyze_with(&config, dir.path());
+    let lines: Vec<usize> = findings.iter().map(|f| f.line).collect();
+    assert_eq!(lines, vec![1], "{:?}", messages(&findings));
+}
+
+// ── Catalog formats ──────────────────────────────────────────────
+
+#[test]
+fn test_yaml_catalogs_with_locale_root() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "config/locales/en.yml",
+        "en:\n  greeting:\n    hello: \"Hello, {name}\"\n  farewell: Bye\n",
+    );
+    write(
+        dir.path(),
+        "config/locales/ja.yml",
+        "ja:\n  greeting:\n    hello: \"こんにちは、{name}\"\n",
+    );
+    write(
+        dir.path(),
+        "app.ts",
+        "t('greeting.hello');\nt('farewell');\n",
+    );
+
+    let findings = analyze(dir.path());
+    assert_eq!(
+        messages(&findings),
+        vec![(
+            "missing_key".to_string(),
+            "Translation key `farewell` is missing from ja".to_string()
+        )]
+    );
+    assert_eq!(findings[0].line, 2);
+}
+
+#[test]
+fn test_gettext_catalogs_and_python_lookups() {
+    let dir = TempDir::new().unwrap();
+    let po = |translated: &str| {
+        format!(
+            "msgid \"\"\nmsgstr \"Content-Type: text/plain; charset=UTF-8\\n\"\n\n\
+             msgid \"Welcome back\"\nmsgstr \"{}\"\n\n\
+             #, fuzzy\nmsgid \"Sign out\"\nmsgstr \"Abmelden\"\n\n\
+             msgid \"Unused string\"\nmsgstr \"x\"\n",
+            translated
+        )
+    };
+    write(
+        dir.path(),
+        "app/locale/de/LC_MESSAGES/messages.po",
+        &po("Willkommen zurück"),
+    );
+    write(dir.path(), "app/locale/fr/LC_MESSAGES/messages.po", &po(""));
+    write(
+        dir.path(),
+        "app/views.py",
+        "from gettext import gettext as _\n\ndef header():\n    return [_(\"Welcome back\"), _(\"Sign out\"), _(f\"Hi {name}\")]\n",
+    );
+
+    let findings = analyze(dir.path());
+    assert_eq!(
+        messages(&findings),
+        vec![
+            (
+                "dynamic_key".to_string(),
+                "Translation key `f\"Hi {name}\"` is built at runtime and cannot be checked against the catalogs"
+                    .to_string()
+            ),
+            (
+                "missing_key".to_string(),
+                "Translation key `Sign out` is missing from de, fr".to_string()
+            ),
+            (
+                "missing_key".to_string(),
+                "Translation key `Welcome back` is missing from fr".to_string()
+            ),
+            (
+                "unused_key".to_string(),
+                "Translation key `Unused string` is never looked up (de, fr)".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_invalid_catalog_is_skipped() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "locales/en.json", "{ not json");
+    write(dir.path(), "app.ts", "t('a');\n");
+    assert!(analyze(dir.path()).is_empty());
+}
+
+#[test]
+fn test_no_catalogs_no_findings() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "app.ts", "t('a');\nt(key);\n");
+    assert!(analyze(dir.path()).is_empty());
+}
+
+// ── Namespaces ───────────────────────────────────────────────────
+
+#[test]
+fn test_missing_namespace_replaces_per_key_findings() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "locales/en/common.json", r#"{"ok": "OK"}"#);
+    write(
+        dir.path(),
+        "locales/en/billing.json",
+        r#"{"title": "Billing"}"#,
+    );
+    write(dir.path(), "locales/de/common.json", r#"{"ok": "OK"}"#);
+    write(
+        dir.path(),
+        "app.ts",
+        "t('common:ok');\nt('billing:title');\nt('billing.title');\n",
+    );
+
+    let findings = analyze(dir.path());
+    assert_eq!(findings.len(), 1, "{:?}", messages(&findings));
+    assert_eq!(findings[0].rule.as_deref(), Some("missing_namespace"));
+    assert!(findings[0].file.ends_with("locales/en/billing.json"));
+    assert_eq!(findings[0].line, 1);
+    assert_eq!(
+        findings[0].message,
+        "Locale de has no `billing` namespace (defined in en)"
+    );
+}
+
+#[test]
+fn test_namespaced_lookup_only_matches_its_namespace() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "locales/en/common.json", r#"{"title": "Home"}"#);
+    write(dir.path(), "locales/en/admin.json", r#"{"users": "Users"}"#);
+    write(
+        dir.path(),
+        "app.ts",
+        "t('admin:title');\nt('admin:users');\n",
+    );
+
+    let findings = analyze(dir.path());
+    assert_eq!(
+        messages(&findings),
+        vec![
+            (
+                "missing_key".to_string(),
+                "Translation key `title` is missing from en".to_string()
+            ),
+            (
+                "unused_key".to_string(),
+                "Translation key `title` is never looked up (en)".to_string()
+            ),
+        ]
+    );
+}
+
+// ── Configuration ────────────────────────────────────────────────
+
+#[test]
+fn test_sub_rule_selection() {
+    let root = common::fixture_root(FIXTURE);
+    let mut config = RevetConfig::from_file(&root.join(".revet.toml")).unwrap();
+    config.analyzers.i18n.selection.disable = vec!["unused_key".to_string()];
+    assert_eq!(
+        summary(&common::run(&root, EXTENSIONS, &config), &root),
+        vec![row("src/components/Checkout.tsx", 9, "i18n:missing_key")]
+    );
+}
+
+#[test]
+fn test_custom_catalog_pattern() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "i18n/messages.en.json", r#"{"a": "A"}"#);
+    write(dir.path(), "i18n/messages.nl.json", r#"{}"#);
+    write(dir.path(), "app.ts", "t('a');\n");
+
+    assert!(analyze(dir.path()).is_empty());
+    let config = i18n("[analyzers.i18n]\ncatalogs = [\"i18n/messages.{locale}.json\"]");
+    let findings = analyze_with(&config, dir.path());
+    assert_eq!(
+        messages(&findings),
+        vec![(
+            "missing_key".to_string(),
+            "Translation key `a` is missing from nl".to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_catalog_pattern() {
+    let pattern = CatalogPattern::parse("**/locales/{locale}/{namespace}.json").unwrap();
+    assert_eq!(
+        pattern.captures("web/public/locales/pt-BR/common.json"),
+        Some(("pt-BR".to_string(), Some("common".to_string())))
+    );
+    assert_eq!(
+        pattern.captures("locales/en/common.json"),
+        Some(("en".to_string(), Some("common".to_string())))
+    );
+    assert_eq!(pattern.captures("locales/en/nested/common.json"), None);
+    assert_eq!(pattern.captures("locales/Shared/common.json"), None);
+
+    assert!(CatalogPattern::parse("locales/*.json").is_none());
+    assert!(CatalogPattern::parse("{locale}/{locale}.json").is_none());
+    assert!(CatalogPattern::parse("{lang}/{locale}.json").is_none());
+}
+
+#[test]
+fn test_validate_i18n_config() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[analyzers.i18n]
+catalogs = ["locales/*.json"]
+frameworks = ["i18next", "formatjs"]
+default_locale = ""
+only = ["missing_keys"]
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    for expected in [
+        "[analyzers.i18n] catalogs entry \"locales/*.json\"",
+        "[analyzers.i18n] framework \"formatjs\"",
+        "[analyzers.i18n] default_locale",
+        "[analyzers.i18n] unknown sub-rule \"missing_keys\"",
+    ] {
+        assert!(
+            errors.iter().any(|e| e.starts_with(expected)),
+            "missing {:?} in {:?}",
+            expected,
+            errors
+        );
+    }
+    assert!(RevetConfig::default().validate().0.is_empty());
+}
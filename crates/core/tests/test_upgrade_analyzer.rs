@@ -0,0 +1,421 @@
+//! Integration tests for UpgradeAnalyzer and the upgrade report
+
+mod common;
+
+use revet_core::analyzer::upgrade::pack::{Pack, Version};
+use revet_core::analyzer::upgrade::report::UpgradeReport;
+use revet_core::analyzer::upgrade::UpgradeAnalyzer;
+use revet_core::analyzer::GraphAnalyzer;
+use revet_core::config::RevetConfig;
+use revet_core::discovery::discover_files;
+use revet_core::finding::{Finding, Severity};
+use revet_core::ParserDispatcher;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn analyze(root: &Path, config: &RevetConfig) -> Vec<Finding> {
+    let dispatcher = ParserDispatcher::new();
+    let files = discover_files(root, &dispatcher.supported_extensions(), &[]).unwrap();
+    let (graph, _) = dispatcher.parse_files_parallel(&files, root.to_path_buf());
+    UpgradeAnalyzer::new().analyze_graph(&graph, config)
+}
+
+fn with_targets(targets: &[(&str, &str)]) -> RevetConfig {
+    let mut config = RevetConfig::default();
+    for (runtime, version) in targets {
+        config
+            .upgrade
+            .targets
+            .insert(runtime.to_string(), version.to_string());
+    }
+    config
+}
+
+/// (file name, line, sub-rule, severity) for each finding, sorted
+fn summary(findings: &[Finding]) -> Vec<(String, usize, String, Severity)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .map(|f| {
+            (
+                f.file.file_name().unwrap().to_string_lossy().into_owned(),
+                f.line,
+                f.rule.clone().unwrap_or_default(),
+                f.severity,
+            )
+        })
+        .collect();
+    rows.sort_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+    rows
+}
+
+fn row(
+    file: &str,
+    line: usize,
+    rule: &str,
+    severity: Severity,
+) -> (String, usize, String, Severity) {
+    (file.to_string(), line, rule.to_string(), severity)
+}
+
+fn write(dir: &Path, file: &str, content: &str) {
+    let path = dir.join(file);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+// ── Fixtures ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_python_fixture_at_two_targets() {
+    let root = common::fixture_root("upgrade/python");
+
+    // Inferred from requires-python: 3.11 still has distutils
+    let findings = analyze(&root, &RevetConfig::default());
+    assert_eq!(
+        summary(&findings),
+        vec![row("release.py", 4, "deprecated", Severity::Warning)]
+    );
+    assert_eq!(
+        findings[0].message,
+        "`distutils` is deprecated since Python 3.10 and removed in 3.12 (target 3.11)"
+    );
+
+    let findings = analyze(&root, &with_targets(&[("python", "3.12")]));
+    assert_eq!(
+        summary(&findings),
+        vec![row("release.py", 4, "removed", Severity::Error)]
+    );
+    assert_eq!(
+        findings[0].message,
+        "`distutils` was removed in Python 3.12 (target 3.12)"
+    );
+    assert_eq!(
+        findings[0].suggestion.as_deref(),
+        Some("Use setuptools, or `packaging` for version handling instead")
+    );
+}
+
+#[test]
+fn test_node_fixture_at_two_targets() {
+    let root = common::fixture_root("upgrade/node");
+
+    let findings = analyze(&root, &RevetConfig::default());
+    assert_eq!(
+        summary(&findings),
+        vec![row("vault.js", 8, "deprecated", Severity::Warning)]
+    );
+
+    let findings = analyze(&root, &with_targets(&[("node", "22")]));
+    assert_eq!(
+        summary(&findings),
+        vec![row("vault.js", 8, "removed", Severity::Error)]
+    );
+    assert_eq!(
+        findings[0].message,
+        "`crypto.createCipher` was removed in Node.js 22 (target 22)"
+    );
+}
+
+// ── Matching ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_named_apis_are_reported_at_each_call() {
+    let tmp = TempDir::new().unwrap();
+    write(
+        tmp.path(),
+        "jobs.py",
+        "import asyncio\nimport unittest\nfrom collections import Mapping\n\n\
+         def run():\n    loop = asyncio.get_event_loop()\n    asyncio.sleep(1)\n\
+         \n\ndef suite():\n    return unittest.makeSuite(Case)\n",
+    );
+    let findings = analyze(tmp.path(), &with_targets(&[("python", "3.14")]));
+    assert_eq!(
+        summary(&findings),
+        vec![
+            // Imported but never called: reported at the import
+            row("jobs.py", 3, "removed", Severity::Error),
+            row("jobs.py", 6, "changed", Severity::Warning),
+            row("jobs.py", 11, "removed", Severity::Error),
+        ]
+    );
+    assert!(findings[1]
+        .message
+        .ends_with("it raises RuntimeError when no event loop is set"));
+
+    // Deprecated without a scheduled removal is informational
+    let findings = analyze(tmp.path(), &with_targets(&[("python", "3.12")]));
+    assert_eq!(
+        summary(&findings),
+        vec![
+            row("jobs.py", 3, "removed", Severity::Error),
+            row("jobs.py", 6, "deprecated", Severity::Info),
+            row("jobs.py", 11, "deprecated", Severity::Warning),
+        ]
+    );
+}
+
+#[test]
+fn test_node_prefix_submodules_and_local_modules() {
+    let tmp = TempDir::new().unwrap();
+    write(
+        tmp.path(),
+        "punycode.js",
+        "export function toASCII(s) { return s; }\n",
+    );
+    write(
+        tmp.path(),
+        "app.js",
+        "import { parse } from 'node:url';\nimport { toASCII } from './punycode';\n\n\
+         export function host(s) {\n  return toASCII(parse(s).host);\n}\n",
+    );
+    let findings = analyze(tmp.path(), &with_targets(&[("node", "21")]));
+    assert_eq!(
+        summary(&findings),
+        vec![row("app.js", 5, "deprecated", Severity::Info)]
+    );
+    assert!(findings[0].message.starts_with("`url.parse`"));
+}
+
+#[test]
+fn test_nothing_is_reported_without_a_target_or_before_it() {
+    let tmp = TempDir::new().unwrap();
+    write(
+        tmp.path(),
+        "legacy.py",
+        "import imp\nimport distutils.core\n",
+    );
+    assert!(analyze(tmp.path(), &RevetConfig::default()).is_empty());
+    assert!(analyze(tmp.path(), &with_targets(&[("python", "3.3")])).is_empty());
+    // Submodules of a whole-module entry count
+    assert_eq!(
+        summary(&analyze(tmp.path(), &with_targets(&[("python", "3.10")]))),
+        vec![
+            row("legacy.py", 1, "deprecated", Severity::Warning),
+            row("legacy.py", 2, "deprecated", Severity::Warning),
+        ]
+    );
+}
+
+// ── Targets ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_targets_come_from_the_nearest_manifest() {
+    let tmp = TempDir::new().unwrap();
+    write(
+        tmp.path(),
+        "pyproject.toml",
+        "[tool.poetry.dependencies]\npython = \"^3.12\"\n",
+    );
+    write(
+        tmp.path(),
+        "legacy/pyproject.toml",
+        "[project]\nrequires-python = \"~=3.9\"\n",
+    );
+    write(tmp.path(), "app/tasks.py", "import imp\n");
+    write(tmp.path(), "legacy/tasks.py", "import imp\n");
+    let findings = analyze(tmp.path(), &RevetConfig::default());
+    let rules: Vec<(String, String)> = findings
+        .iter()
+        .map(|f| {
+            let file = f.file.strip_prefix(tmp.path()).unwrap();
+            (file.display().to_string(), f.rule.clone().unwrap())
+        })
+        .collect();
+    assert_eq!(
+        rules,
+        vec![
+            ("app/tasks.py".to_string(), "removed".to_string()),
+            ("legacy/tasks.py".to_string(), "deprecated".to_string()),
+        ]
+    );
+
+    // A declared target wins over every manifest
+    let findings = analyze(tmp.path(), &with_targets(&[("python", "3.11")]));
+    assert!(findings
+        .iter()
+        .all(|f| f.rule.as_deref() == Some("deprecated")));
+}
+
+#[test]
+fn test_lower_bound_of_version_ranges() {
+    let cases = [
+        (">=3.9", Some("3.9")),
+        (">=3.9,<4", Some("3.9")),
+        (">= 3.10, != 3.10.1", Some("3.10")),
+        ("~=3.11", Some("3.11")),
+        ("^3.12", Some("3.12")),
+        ("^18 || ^20", Some("18")),
+        (">=20 <23", Some("20")),
+        ("18.x", Some("18")),
+        ("v20.11.1", Some("20.11.1")),
+        ("<4", None),
+        ("*", None),
+    ];
+    for (range, expected) in cases {
+        assert_eq!(
+            Version::lower_bound(range).map(|v| v.to_string()),
+            expected.map(String::from),
+            "{}",
+            range
+        );
+    }
+    assert_eq!(Version::parse("3.12"), Version::parse("3.12.0"));
+    assert!(Version::parse("3.9") < Version::parse("3.10"));
+}
+
+// ── Packs ─────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_configured_pack_replaces_the_bundled_one() {
+    let tmp = TempDir::new().unwrap();
+    write(
+        tmp.path(),
+        "packs/python.toml",
+        "runtime = \"python\"\nname = \"Python\"\nextensions = [\"py\"]\n\n\
+         [[api]]\nmodule = \"legacy_rpc\"\nremoved = \"3.12\"\nreplacement = \"grpc\"\n",
+    );
+    write(tmp.path(), "app.py", "import imp\nimport legacy_rpc\n");
+    let mut config = with_targets(&[("python", "3.12")]);
+    config.upgrade.packs = vec!["packs/python.toml".to_string()];
+    let findings = analyze(tmp.path(), &config);
+    assert_eq!(
+        summary(&findings),
+        vec![row("app.py", 2, "removed", Severity::Error)]
+    );
+    assert_eq!(findings[0].suggestion.as_deref(), Some("Use grpc instead"));
+}
+
+#[test]
+fn test_invalid_pack_is_reported() {
+    let tmp = TempDir::new().unwrap();
+    write(
+        tmp.path(),
+        "packs/ruby.toml",
+        "runtime = \"ruby\"\nextensions = [\"rb\"]\n\n[[api]]\nmodule = \"net/ftp\"\nremoved = \"three\"\n",
+    );
+    write(tmp.path(), "app.py", "import imp\n");
+    let mut config = with_targets(&[("python", "3.12")]);
+    config.upgrade.packs = vec![
+        "packs/ruby.toml".to_string(),
+        "packs/missing.toml".to_string(),
+    ];
+    let findings = analyze(tmp.path(), &config);
+    let invalid: Vec<_> = findings
+        .iter()
+        .filter(|f| f.rule.as_deref() == Some("invalid_pack"))
+        .collect();
+    assert_eq!(invalid.len(), 2, "{:#?}", findings);
+    assert!(invalid[0]
+        .message
+        .contains("removed = \"three\" is not a version"));
+    assert!(invalid[0].file.ends_with("packs/ruby.toml"));
+    assert!(invalid[1].file.ends_with("packs/missing.toml"));
+    // The bundled packs still apply
+    assert!(findings
+        .iter()
+        .any(|f| f.rule.as_deref() == Some("removed")));
+
+    let error = Pack::parse("runtime = \"go\"\nextensions = [\"go\"]\n[[api]]\nmodule = \"x\"\n")
+        .unwrap_err();
+    assert!(error.message.contains("needs at least one of"), "{}", error);
+    let error = Pack::parse("runtime = \"go\"\n\nextensions = 3\n").unwrap_err();
+    assert_eq!(error.line, 3);
+}
+
+#[test]
+fn test_config_validation() {
+    let config: RevetConfig =
+        toml::from_str("[upgrade]\npython = \"three\"\ndeno = \"2\"\nnode = \"22\"\n").unwrap();
+    let (errors, warnings) = config.validate();
+    assert!(
+        errors
+            .iter()
+            .any(|e| e.contains("[upgrade] python = \"three\"")),
+        "{:?}",
+        errors
+    );
+    assert!(
+        warnings.iter().any(|w| w.contains("[upgrade] deno")),
+        "{:?}",
+        warnings
+    );
+    assert!(!errors
+        .iter()
+        .chain(&warnings)
+        .any(|m| m.contains("[upgrade] node")));
+}
+
+// ── Report ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_report_counts_blockers_per_module() {
+    let tmp = TempDir::new().unwrap();
+    write(
+        tmp.path(),
+        "tools/build.py",
+        "from distutils.core import setup\nimport imp\nimport asyncio\n\n\
+         def run():\n    asyncio.get_event_loop()\n",
+    );
+    write(tmp.path(), "tools/more.py", "import distutils\n");
+    write(
+        tmp.path(),
+        "web/app.js",
+        "import { parse } from 'url';\nparse('x');\n",
+    );
+    let report = UpgradeReport::from_repo(
+        tmp.path(),
+        &with_targets(&[("python", "3.13"), ("node", "22")]),
+    )
+    .unwrap();
+
+    let python = report
+        .runtimes
+        .iter()
+        .find(|r| r.runtime == "python")
+        .unwrap();
+    assert_eq!(python.targets, vec!["3.13"]);
+    assert!(!python.ready);
+    assert_eq!((python.blockers, python.warnings), (3, 1));
+    let modules: Vec<(&str, usize, usize)> = python
+        .modules
+        .iter()
+        .map(|m| (m.module.as_str(), m.blockers, m.warnings))
+        .collect();
+    assert_eq!(
+        modules,
+        vec![("distutils", 2, 0), ("imp", 1, 0), ("asyncio", 0, 1)]
+    );
+
+    let node = report
+        .runtimes
+        .iter()
+        .find(|r| r.runtime == "node")
+        .unwrap();
+    assert!(node.ready);
+    assert_eq!((node.blockers, node.warnings), (0, 1));
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["runtimes"][0]["runtime"], "node");
+    assert_eq!(json["runtimes"][1]["usages"][0]["status"], "removed");
+}
+
+#[test]
+fn test_report_lists_targeted_runtimes_without_uses_as_ready() {
+    let tmp = TempDir::new().unwrap();
+    write(
+        tmp.path(),
+        "package.json",
+        "{\"engines\": {\"node\": \">=22\"}}",
+    );
+    write(
+        tmp.path(),
+        "index.js",
+        "import { createHash } from 'crypto';\ncreateHash('sha1');\n",
+    );
+    let report = UpgradeReport::from_repo(tmp.path(), &RevetConfig::default()).unwrap();
+    assert_eq!(report.runtimes.len(), 1);
+    assert_eq!(report.runtimes[0].runtime, "node");
+    assert_eq!(report.runtimes[0].targets, vec!["22"]);
+    assert!(report.runtimes[0].ready);
+}
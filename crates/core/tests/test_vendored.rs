@@ -0,0 +1,190 @@
+//! `[[vendored]]` resolution, line classification and validation
+
+mod common;
+
+use common::git;
+use revet_core::diff::DiffFileLines;
+use revet_core::generated::GeneratedPolicy;
+use revet_core::{CoverageLedger, Finding, RevetConfig, SkipReason, VendoredFiles};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn config(toml: &str) -> RevetConfig {
+    toml::from_str(toml).unwrap()
+}
+
+fn finding(file: &Path, line: usize) -> Finding {
+    Finding {
+        id: "SEC-001".to_string(),
+        file: file.to_path_buf(),
+        line,
+        ..Default::default()
+    }
+}
+
+/// `vendor/lib` tagged `upstream` with `a.txt` and `b.txt`, then `a.txt`
+/// patched on line 2 and `c.txt` added
+fn repo() -> (TempDir, Vec<PathBuf>) {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path();
+    let lib = path.join("vendor/lib");
+    fs::create_dir_all(&lib).unwrap();
+    fs::write(lib.join("a.txt"), "one\ntwo\nthree\n").unwrap();
+    fs::write(lib.join("b.txt"), "one\n").unwrap();
+    git(path, &["init", "-q", "-b", "main"]);
+    git(path, &["add", "."]);
+    git(path, &["commit", "-q", "-m", "vendor"]);
+    git(path, &["tag", "upstream"]);
+    fs::write(lib.join("a.txt"), "one\nTWO\nthree\n").unwrap();
+    fs::write(lib.join("c.txt"), "new\n").unwrap();
+    fs::write(path.join("own.txt"), "mine\n").unwrap();
+    let files = [
+        "vendor/lib/a.txt",
+        "vendor/lib/b.txt",
+        "vendor/lib/c.txt",
+        "own.txt",
+    ]
+    .iter()
+    .map(|f| path.join(f))
+    .collect();
+    (dir, files)
+}
+
+#[test]
+fn test_classify_and_apply() {
+    let (dir, files) = repo();
+    let root = dir.path();
+    let vendored = VendoredFiles::resolve(
+        &config("[[vendored]]\npath = \"vendor/lib\"\nupstream_ref = \"upstream\"\n"),
+        root,
+    );
+    assert!(vendored.overlays()[0].tree.is_some());
+    let set = vendored.classify(&files, root);
+    assert_eq!(set.len(), 3);
+    match set.patched_lines(Path::new("vendor/lib/a.txt"), root) {
+        Some(DiffFileLines::Lines(lines)) => assert_eq!(lines.len(), 1),
+        other => panic!("{:?}", other),
+    }
+    assert!(matches!(
+        set.patched_lines(&files[2], root),
+        Some(DiffFileLines::AllNew)
+    ));
+    assert_eq!(
+        set.analysis_files(&files),
+        [files[0].clone(), files[2].clone(), files[3].clone()]
+    );
+
+    let findings = vec![
+        finding(&files[0], 1),
+        finding(&files[0], 2),
+        finding(&files[1], 1),
+        finding(&files[2], 1),
+        finding(&files[3], 1),
+        finding(Path::new(""), 0),
+    ];
+    let (kept, dropped) = set.apply(findings, root);
+    assert_eq!(dropped, 2);
+    let kept: Vec<(&Path, usize)> = kept.iter().map(|f| (f.file.as_path(), f.line)).collect();
+    assert_eq!(
+        kept,
+        [
+            (files[0].as_path(), 2),
+            (files[2].as_path(), 1),
+            (files[3].as_path(), 1),
+            (Path::new(""), 0)
+        ]
+    );
+
+    let mut ledger = CoverageLedger::new();
+    set.record(&mut ledger);
+    assert_eq!(
+        ledger.skip_reason(&files[1]),
+        Some(SkipReason::VendoredUpstream)
+    );
+    assert_eq!(ledger.summary().vendored, 3);
+}
+
+#[test]
+fn test_unresolved_upstream() {
+    let (dir, files) = repo();
+    let vendored = VendoredFiles::resolve(
+        &config("[[vendored]]\npath = \"vendor/lib\"\nupstream_ref = \"v9\"\n[files]\ngenerated = \"info-only\"\n"),
+        dir.path(),
+    );
+    let overlay = &vendored.overlays()[0];
+    assert_eq!(overlay.tree, None);
+    assert!(overlay.error.as_deref().unwrap().contains("'v9'"));
+    assert_eq!(overlay.fallback, GeneratedPolicy::InfoOnly);
+    let set = vendored.classify(&files, dir.path());
+    assert!(set.patched_lines(&files[0], dir.path()).is_none());
+    assert_eq!(set.analysis_files(&files).len(), 4);
+
+    // No upstream_ref is not an error
+    let vendored = VendoredFiles::resolve(
+        &config("[[vendored]]\npath = \"vendor/lib\"\nfallback = \"skip\"\n"),
+        dir.path(),
+    );
+    assert_eq!(vendored.overlays()[0].error, None);
+    let set = vendored.classify(&files, dir.path());
+    assert_eq!(set.analysis_files(&files), [files[3].clone()]);
+}
+
+#[test]
+fn test_validation() {
+    let invalid = config(
+        r#"
+[[vendored]]
+path = ""
+
+[[vendored]]
+path = "/opt/lib"
+
+[[vendored]]
+path = "../lib"
+
+[[vendored]]
+path = "third_party/a"
+upstream_ref = ""
+fallback = "ignore"
+
+[[vendored]]
+path = "third_party/a/sub"
+"#,
+    );
+    let (errors, _) = invalid.validate();
+    let errors: Vec<&String> = errors
+        .iter()
+        .filter(|e| e.contains("[[vendored]]"))
+        .collect();
+    assert_eq!(errors.len(), 6, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("#1: path must not be empty")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("\"/opt/lib\" must be relative")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("\"../lib\" must be relative")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("third_party/a: upstream_ref must not be empty")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("fallback = \"ignore\" is invalid")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("\"third_party/a/sub\" is listed twice or inside")));
+
+    // Sibling directories sharing a prefix don't overlap
+    let (errors, _) = config(
+        "[[vendored]]\npath = \"third_party/a\"\n[[vendored]]\npath = \"third_party/ab\"\nfallback = \"analyze\"\n",
+    )
+    .validate();
+    assert!(
+        !errors.iter().any(|e| e.contains("[[vendored]]")),
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,422 @@
+//! Integration tests for WebsecAnalyzer
+
+mod common;
+
+use revet_core::analyzer::websec::WebsecAnalyzer;
+use revet_core::analyzer::{Analyzer, AnalyzerDispatcher};
+use revet_core::config::RevetConfig;
+use revet_core::finding::{Confidence, Finding, InsertContext, Severity};
+use std::path::Path;
+use tempfile::TempDir;
+
+const FIXTURE: &str = "websec";
+const EXTENSIONS: &[&str] = &[".py", ".ts", ".js", ".java", ".rb"];
+
+/// (repo-relative file, line, sub-rule) for each WEBSEC finding, sorted
+fn summary(findings: &[Finding], root: &Path) -> Vec<(String, usize, String)> {
+    let mut rows: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("WEBSEC"))
+        .map(|f| {
+            let file = f.file.strip_prefix(root).unwrap_or(&f.file);
+            (
+                file.to_string_lossy().replace('\\', "/"),
+                f.line,
+                f.rule.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    rows.sort();
+    rows
+}
+
+fn row(file: &str, line: usize, rule: &str) -> (String, usize, String) {
+    (file.to_string(), line, format!("websec:{}", rule))
+}
+
+fn analyze(file: &str, content: &str) -> Vec<Finding> {
+    let tmp = TempDir::new().unwrap();
+    WebsecAnalyzer::new().analyze_source(&tmp.path().join(file), content, tmp.path())
+}
+
+/// (line, sub-rule) pairs
+fn lines(findings: &[Finding]) -> Vec<(usize, String)> {
+    findings
+        .iter()
+        .map(|f| (f.line, f.rule.clone().unwrap_or_default()))
+        .collect()
+}
+
+fn at(line: usize, rule: &str) -> (usize, String) {
+    (line, rule.to_string())
+}
+
+/// The replacement snippet of a finding's suggested code
+fn snippet(finding: &Finding) -> &str {
+    let code = finding.suggestion_code.as_ref().expect("suggested code");
+    assert!(matches!(code.insert_context, InsertContext::Replace { .. }));
+    &code.snippet
+}
+
+#[test]
+fn test_fixture_findings() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let expected = vec![
+        row("django/settings.py", 5, "security_headers_missing"),
+        row("django/settings.py", 10, "cors_wildcard_credentials"),
+        row("django/settings.py", 13, "cookie_secure"),
+        row("django/settings.py", 14, "cookie_samesite"),
+        row("django/settings.py", 15, "security_headers_disabled"),
+        row("django/settings.py", 16, "cookie_httponly"),
+        row("django/views.py", 13, "cookie_samesite"),
+        row("express/app.js", 5, "security_headers_missing"),
+        row("express/app.js", 7, "cors_wildcard_credentials"),
+        row("express/app.js", 8, "cookie_httponly"),
+        row("express/app.js", 11, "cookie_httponly"),
+        row("express/app.js", 11, "cookie_samesite"),
+        row("express/app.js", 11, "cookie_secure"),
+        row("express/configure.js", 5, "cors_wildcard_credentials"),
+        row("express/configure.js", 10, "cookie_secure"),
+        row("fastify/server.ts", 7, "cors_wildcard_credentials"),
+        row("fastify/server.ts", 8, "security_headers_disabled"),
+        row("fastify/server.ts", 11, "cookie_samesite"),
+        row("flask/app.py", 4, "security_headers_missing"),
+        row("flask/app.py", 5, "cookie_secure"),
+        row("flask/app.py", 6, "cors_wildcard_credentials"),
+        row("flask/app.py", 12, "cookie_httponly"),
+        row("flask/headers_off.py", 5, "security_headers_disabled"),
+        row("flask/headers_off.py", 5, "security_headers_disabled"),
+        row(
+            "rails/app/controllers/sessions_controller.rb",
+            3,
+            "cookie_secure",
+        ),
+        row(
+            "rails/app/controllers/sessions_controller.rb",
+            4,
+            "cookie_secure",
+        ),
+        row(
+            "rails/config/application.rb",
+            3,
+            "security_headers_disabled",
+        ),
+        row("rails/config/application.rb", 4, "cookie_secure"),
+        row(
+            "rails/config/application.rb",
+            8,
+            "cors_wildcard_credentials",
+        ),
+        row(
+            "spring/AccountController.java",
+            10,
+            "cors_wildcard_credentials",
+        ),
+        row("spring/AccountController.java", 15, "cookie_secure"),
+        row(
+            "spring/CorsProperties.java",
+            14,
+            "cors_wildcard_credentials",
+        ),
+        row(
+            "spring/SecurityConfig.java",
+            14,
+            "security_headers_disabled",
+        ),
+        row(
+            "spring/SecurityConfig.java",
+            21,
+            "cors_wildcard_credentials",
+        ),
+        row("spring/SecurityConfig.java", 29, "cookie_secure"),
+        row("spring/SecurityConfig.java", 30, "cookie_samesite"),
+    ];
+    assert_eq!(summary(&findings, &root), expected);
+}
+
+#[test]
+fn test_correct_configurations_pass() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    for clean in [
+        "express/safe.js",
+        "fastify/safe.ts",
+        "flask/safe.py",
+        "django/settings_production.py",
+        "spring/SafeConfig.java",
+        "rails/config/environments/production.rb",
+    ] {
+        assert!(
+            findings.iter().all(|f| f.file != root.join(clean)),
+            "{} has findings",
+            clean
+        );
+    }
+}
+
+#[test]
+fn test_confidence_and_default_severities() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let of = |rule: &str| -> Vec<&Finding> {
+        findings
+            .iter()
+            .filter(|f| f.rule.as_deref() == Some(rule))
+            .collect()
+    };
+    assert!(of("websec:cors_wildcard_credentials")
+        .iter()
+        .all(|f| f.severity == Severity::Error));
+    for rule in [
+        "websec:cookie_httponly",
+        "websec:cookie_secure",
+        "websec:cookie_samesite",
+        "websec:security_headers_disabled",
+    ] {
+        assert!(of(rule).iter().all(|f| f.severity == Severity::Warning));
+    }
+    assert!(of("websec:security_headers_missing")
+        .iter()
+        .all(|f| f.severity == Severity::Info && f.confidence == Confidence::Medium));
+
+    // Values read from configuration or the environment can only be checked
+    // at runtime: those findings are Low and come without a fix
+    let mut low: Vec<(String, usize)> = findings
+        .iter()
+        .filter(|f| f.confidence == Confidence::Low)
+        .map(|f| {
+            assert!(f.suggestion_code.is_none());
+            assert!(f.message.contains("at runtime"), "{}", f.message);
+            let file = f.file.strip_prefix(&root).unwrap();
+            (file.to_string_lossy().replace('\\', "/"), f.line)
+        })
+        .collect();
+    low.sort();
+    assert_eq!(
+        low,
+        vec![
+            ("django/settings.py".to_string(), 16),
+            ("django/views.py".to_string(), 13),
+            ("express/configure.js".to_string(), 5),
+            ("express/configure.js".to_string(), 10),
+            (
+                "rails/app/controllers/sessions_controller.rb".to_string(),
+                4
+            ),
+            ("spring/CorsProperties.java".to_string(), 14),
+        ]
+    );
+}
+
+#[test]
+fn test_messages_name_the_framework_pattern() {
+    let (root, findings) = common::fixture_findings(FIXTURE, EXTENSIONS);
+    let patterns = [
+        ("express/app.js", 7, "Express cors()"),
+        ("express/app.js", 8, "express-session"),
+        ("fastify/server.ts", 8, "Fastify @fastify/helmet"),
+        ("flask/headers_off.py", 5, "Flask-Talisman"),
+        ("django/settings.py", 5, "Django MIDDLEWARE"),
+        ("spring/SecurityConfig.java", 14, "Spring Security headers"),
+        ("spring/AccountController.java", 10, "Spring @CrossOrigin"),
+        ("rails/config/application.rb", 8, "rack-cors"),
+    ];
+    for (file, line, pattern) in patterns {
+        let finding = findings
+            .iter()
+            .find(|f| f.file == root.join(file) && f.line == line)
+            .unwrap();
+        assert!(
+            finding.message.starts_with(pattern),
+            "{}: {}",
+            file,
+            finding.message
+        );
+    }
+}
+
+#[test]
+fn test_suggested_fixes() {
+    let findings = analyze(
+        "app.js",
+        "app.use(cors({ origin: '*', credentials: true }));\n",
+    );
+    assert_eq!(
+        snippet(&findings[0]),
+        "origin: [\"https://app.example.com\"]"
+    );
+
+    let findings = analyze(
+        "settings.py",
+        "CORS_ALLOW_ALL_ORIGINS = True\nCORS_ALLOW_CREDENTIALS = True\nSESSION_COOKIE_SECURE = False\n",
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![at(1, "cors_wildcard_credentials"), at(3, "cookie_secure")]
+    );
+    assert_eq!(
+        snippet(&findings[0]),
+        "CORS_ALLOWED_ORIGINS = [\"https://app.example.com\"]"
+    );
+    assert_eq!(snippet(&findings[1]), "SESSION_COOKIE_SECURE = True");
+
+    let findings = analyze(
+        "Cookies.java",
+        "\
+class Cookies {
+    void remember(HttpServletResponse response) {
+        Cookie cookie = new Cookie(\"remember\", \"token\");
+        cookie.setSecure(true);
+        response.addCookie(cookie);
+    }
+}
+",
+    );
+    assert_eq!(lines(&findings), vec![at(3, "cookie_httponly")]);
+    assert_eq!(
+        snippet(&findings[0]),
+        "Cookie cookie = new Cookie(\"remember\", \"token\");\n        cookie.setHttpOnly(true);"
+    );
+
+    let findings = analyze("cookies.rb", "cookies[:theme] = \"dark\"\n");
+    assert_eq!(
+        lines(&findings),
+        vec![at(1, "cookie_httponly"), at(1, "cookie_secure")]
+    );
+    assert_eq!(
+        snippet(&findings[0]),
+        "cookies[:theme] = { value: \"dark\", httponly: true }"
+    );
+    assert!(findings[0].message.contains("`theme`"));
+}
+
+#[test]
+fn test_express_session_defaults() {
+    // express-session turns httpOnly on by itself, but not secure or sameSite
+    let findings = analyze(
+        "app.js",
+        "app.use(session({ secret: 'keyboard cat' }));\napp.use(cookieSession({ name: 'auth', secure: true, sameSite: 'strict' }));\n",
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![at(1, "cookie_secure"), at(1, "cookie_samesite")]
+    );
+    assert_eq!(
+        snippet(&findings[0]),
+        "{ secret: 'keyboard cat', cookie: { secure: true, sameSite: \"lax\" } }"
+    );
+}
+
+#[test]
+fn test_trusted_origin_checks() {
+    // Functions and explicit lists decide per origin
+    assert!(analyze(
+        "app.js",
+        "app.use(cors({ origin: (origin, cb) => cb(null, allowed.has(origin)), credentials: true }));\napp.use(cors({ origin: '*' }));\n",
+    )
+    .is_empty());
+    assert!(analyze(
+        "app.py",
+        "CORS(app, resources={r\"/api/*\": {\"origins\": \"https://app.example.com\"}}, supports_credentials=True)\n",
+    )
+    .is_empty());
+    assert!(analyze(
+        "Api.java",
+        "@CrossOrigin(origins = \"https://app.example.com\", allowCredentials = \"true\")\nclass Api {}\n",
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_disabled_headers() {
+    let findings = analyze(
+        "Security.java",
+        "\
+class Security {
+    SecurityFilterChain chain(HttpSecurity http) throws Exception {
+        http.headers(headers -> headers.contentTypeOptions(ContentTypeOptionsConfig::disable));
+        http.headers().xssProtection().disable();
+        return http.build();
+    }
+}
+",
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![
+            at(3, "security_headers_disabled"),
+            at(4, "security_headers_disabled")
+        ]
+    );
+    assert!(findings[0].message.contains("`contentTypeOptions`"));
+
+    let findings = analyze(
+        "application.rb",
+        "config.action_dispatch.default_headers = {}\nconfig.action_dispatch.default_headers.clear\n",
+    );
+    assert_eq!(
+        lines(&findings),
+        vec![
+            at(1, "security_headers_disabled"),
+            at(2, "security_headers_disabled")
+        ]
+    );
+}
+
+#[test]
+fn test_severity_override_and_selection() {
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("app.js");
+    std::fs::write(
+        &file,
+        "const app = express();\napp.use(cors({ credentials: true }));\nres.cookie('a', 'b', { httpOnly: true, secure: true });\n",
+    )
+    .unwrap();
+    let config: RevetConfig = toml::from_str(
+        "[modules]\nwebsec = true\n\n[analyzers.websec]\ndisable = [\"security_headers_missing\"]\n\n[analyzers.websec.severity]\ncookie_samesite = \"info\"\n",
+    )
+    .unwrap();
+    let findings =
+        AnalyzerDispatcher::new_with_config(&config).run_all(&[file], tmp.path(), &config);
+    let rules: Vec<_> = findings
+        .iter()
+        .filter(|f| f.id.starts_with("WEBSEC"))
+        .map(|f| (f.rule.as_deref().unwrap_or_default(), f.severity))
+        .collect();
+    assert_eq!(
+        rules,
+        vec![
+            ("websec:cors_wildcard_credentials", Severity::Error),
+            ("websec:cookie_samesite", Severity::Info)
+        ]
+    );
+}
+
+#[test]
+fn test_config_validation() {
+    let config: RevetConfig = toml::from_str(
+        r#"
+[analyzers.websec]
+only = ["cors"]
+
+[analyzers.websec.severity]
+cookie_secure = "loud"
+headers = "info"
+"#,
+    )
+    .unwrap();
+    let (errors, _) = config.validate();
+    let errors: Vec<&String> = errors.iter().filter(|e| e.contains("websec")).collect();
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors.iter().any(|e| e.contains("\"cors\"")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("cookie_secure = \"loud\"")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("unknown sub-rule \"headers\"")));
+
+    assert!(RevetConfig::default()
+        .validate()
+        .0
+        .iter()
+        .all(|e| !e.contains("websec")));
+}
@@ -0,0 +1,81 @@
+use revet_core::{WorkspaceConfig, WORKSPACE_FILE};
+use std::fs;
+use tempfile::TempDir;
+
+fn workspace(toml: &str, dirs: &[&str]) -> (TempDir, WorkspaceConfig) {
+    let tmp = TempDir::new().unwrap();
+    for dir in dirs {
+        fs::create_dir_all(tmp.path().join(dir)).unwrap();
+    }
+    let file = tmp.path().join(WORKSPACE_FILE);
+    fs::write(&file, toml).unwrap();
+    let config = WorkspaceConfig::from_file(&file).unwrap();
+    (tmp, config)
+}
+
+fn names(config: &WorkspaceConfig, tmp: &TempDir) -> Vec<String> {
+    config
+        .resolve_repos(tmp.path())
+        .unwrap()
+        .into_iter()
+        .map(|r| r.name)
+        .collect()
+}
+
+#[test]
+fn test_globs_expand_to_sorted_directories() {
+    let (tmp, config) = workspace(
+        r#"repos = ["services/*"]"#,
+        &["services/web", "services/api", "services/billing"],
+    );
+    fs::write(tmp.path().join("services/README.md"), "not a repo").unwrap();
+
+    assert_eq!(
+        names(&config, &tmp),
+        vec!["services/api", "services/billing", "services/web"]
+    );
+}
+
+#[test]
+fn test_literal_paths_kept_even_when_missing() {
+    let (tmp, config) = workspace(r#"repos = ["libs/shared", "gone/"]"#, &["libs/shared"]);
+
+    let repos = config.resolve_repos(tmp.path()).unwrap();
+    assert_eq!(repos.len(), 2);
+    assert_eq!(repos[0].name, "libs/shared");
+    assert!(repos[0].path.is_absolute());
+    assert_eq!(repos[1].name, "gone");
+    assert!(!repos[1].path.exists());
+}
+
+#[test]
+fn test_exclude_and_dedup() {
+    let (tmp, config) = workspace(
+        r#"
+repos = ["services/api", "services/*"]
+exclude = ["services/legacy-*"]
+"#,
+        &["services/api", "services/legacy-cart", "services/web"],
+    );
+
+    assert_eq!(names(&config, &tmp), vec!["services/api", "services/web"]);
+}
+
+#[test]
+fn test_jobs_zero_rejected() {
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join(WORKSPACE_FILE);
+    fs::write(&file, "repos = [\"a\"]\njobs = 0\n").unwrap();
+
+    let err = WorkspaceConfig::from_file(&file).unwrap_err();
+    assert!(err.to_string().contains("jobs must be at least 1"));
+}
+
+#[test]
+fn test_unknown_keys_rejected() {
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join(WORKSPACE_FILE);
+    fs::write(&file, "repo = [\"a\"]\n").unwrap();
+
+    assert!(WorkspaceConfig::from_file(&file).is_err());
+}
@@ -0,0 +1,166 @@
+//! Committed per-rule metrics and the regression check against them
+//!
+//! ```toml
+//! [tolerance]                   # applies to every rule (defaults: 0)
+//! recall_drop = 0.0
+//! false_positive_increase = 0
+//!
+//! [rules.SEC]
+//! true_positives = 7
+//! false_positives = 1
+//! false_negatives = 0
+//! recall_drop = 0.1             # optional per-rule override
+//! ```
+
+use crate::metrics::{Report, RuleMetrics};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+const HEADER: &str = "# Revet evaluation baseline — regenerate with `cargo run -p revet-eval -- --update`\n# and review the diff: every count change is a precision or recall change.\n\n";
+
+/// Allowed slack before `--check` fails
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Tolerance {
+    /// Largest accepted recall decrease, as a fraction (0.05 = 5 points)
+    #[serde(default)]
+    pub recall_drop: f64,
+    /// Largest accepted increase in false positives
+    #[serde(default)]
+    pub false_positive_increase: usize,
+}
+
+/// Baseline entry for one finding prefix
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleBaseline {
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub false_negatives: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recall_drop: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub false_positive_increase: Option<usize>,
+}
+
+impl RuleBaseline {
+    pub fn metrics(&self) -> RuleMetrics {
+        RuleMetrics {
+            true_positives: self.true_positives,
+            false_positives: self.false_positives,
+            false_negatives: self.false_negatives,
+        }
+    }
+}
+
+/// A rule that got worse than the baseline allows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    pub rule: String,
+    pub message: String,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.rule, self.message)
+    }
+}
+
+/// Contents of `eval/baseline.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Baseline {
+    #[serde(default)]
+    pub tolerance: Tolerance,
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleBaseline>,
+}
+
+impl Baseline {
+    /// Load `path`; a missing file is an empty baseline
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let body = toml::to_string_pretty(self).context("Failed to serialize baseline")?;
+        std::fs::write(path, format!("{HEADER}{body}"))
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Baseline metrics for `rule`, if recorded
+    pub fn get(&self, rule: &str) -> Option<RuleMetrics> {
+        self.rules.get(rule).map(RuleBaseline::metrics)
+    }
+
+    /// Replace the recorded metrics with `report`'s, keeping tolerances of
+    /// rules that are still scored
+    pub fn update(&mut self, report: &Report) {
+        let old = std::mem::take(&mut self.rules);
+        for (rule, m) in &report.rules {
+            let previous = old.get(rule);
+            self.rules.insert(
+                rule.clone(),
+                RuleBaseline {
+                    true_positives: m.true_positives,
+                    false_positives: m.false_positives,
+                    false_negatives: m.false_negatives,
+                    recall_drop: previous.and_then(|p| p.recall_drop),
+                    false_positive_increase: previous.and_then(|p| p.false_positive_increase),
+                },
+            );
+        }
+    }
+
+    /// Every rule whose recall dropped or false positives grew beyond the
+    /// tolerance, plus rules scored but not recorded (the baseline must be
+    /// regenerated so the new numbers are reviewed)
+    pub fn check(&self, report: &Report) -> Vec<Regression> {
+        let mut regressions = Vec::new();
+        for (rule, current) in &report.rules {
+            let Some(base) = self.rules.get(rule) else {
+                regressions.push(Regression {
+                    rule: rule.clone(),
+                    message: "not in the baseline; run `revet-eval --update`".to_string(),
+                });
+                continue;
+            };
+            let recall_drop = base.recall_drop.unwrap_or(self.tolerance.recall_drop);
+            let fp_increase = base
+                .false_positive_increase
+                .unwrap_or(self.tolerance.false_positive_increase);
+
+            let (was, now) = (base.metrics().recall(), current.recall());
+            if now + recall_drop + f64::EPSILON < was {
+                regressions.push(Regression {
+                    rule: rule.clone(),
+                    message: format!(
+                        "recall dropped from {:.1}% to {:.1}% ({} → {} missed)",
+                        was * 100.0,
+                        now * 100.0,
+                        base.false_negatives,
+                        current.false_negatives
+                    ),
+                });
+            }
+            if current.false_positives > base.false_positives + fp_increase {
+                regressions.push(Regression {
+                    rule: rule.clone(),
+                    message: format!(
+                        "false positives rose from {} to {}",
+                        base.false_positives, current.false_positives
+                    ),
+                });
+            }
+        }
+        regressions
+    }
+}
@@ -0,0 +1,166 @@
+//! Corpus format
+//!
+//! Every `<name>.toml` in the corpus directory describes one corpus:
+//!
+//! ```toml
+//! # Tree to analyze, relative to this file (default: the directory `<name>/`)
+//! root = "../../tests/fixtures/python_flask_app"
+//!
+//! # Finding prefixes scored on this corpus (default: the ones in `expect`).
+//! # Findings of other prefixes are ignored.
+//! rules = ["SEC", "SQL"]
+//!
+//! [[expect]]
+//! file = "app.py"      # relative to root
+//! rule = "SQL"         # finding prefix
+//! line = 42
+//! tolerance = 1        # also accept lines 41–43 (default 0)
+//! ```
+//!
+//! The tree is analyzed with its own `.revet.toml` if it has one, and the
+//! default config otherwise; configs of enclosing directories never apply.
+
+use crate::metrics::Observed;
+use anyhow::{bail, Context, Result};
+use revet_core::analyzer::registry::prefix_of;
+use revet_core::{analyze_root, RevetConfig};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// A finding revet should report
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Expectation {
+    /// Path relative to the corpus root, `/`-separated
+    pub file: String,
+    /// Finding prefix, e.g. `"SEC"`
+    pub rule: String,
+    /// Expected 1-based line
+    pub line: usize,
+    /// Lines of slack either side of `line`
+    #[serde(default)]
+    pub tolerance: usize,
+}
+
+impl Expectation {
+    /// Whether a finding at `line` satisfies this expectation
+    pub fn accepts(&self, line: usize) -> bool {
+        line.abs_diff(self.line) <= self.tolerance
+    }
+}
+
+/// Sidecar file contents
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Sidecar {
+    #[serde(default)]
+    root: Option<String>,
+    #[serde(default)]
+    rules: Vec<String>,
+    #[serde(default)]
+    expect: Vec<Expectation>,
+}
+
+/// One annotated fixture tree
+#[derive(Debug, Clone)]
+pub struct Corpus {
+    /// Sidecar file stem
+    pub name: String,
+    /// Directory analyzed
+    pub root: PathBuf,
+    /// Finding prefixes scored
+    pub rules: BTreeSet<String>,
+    /// Declared findings
+    pub expect: Vec<Expectation>,
+}
+
+impl Corpus {
+    /// Load the corpus described by `sidecar`
+    pub fn load(sidecar: &Path) -> Result<Self> {
+        let name = sidecar
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .context("corpus file has no name")?
+            .to_string();
+        let text = std::fs::read_to_string(sidecar)
+            .with_context(|| format!("Failed to read {}", sidecar.display()))?;
+        let spec: Sidecar = toml::from_str(&text)
+            .with_context(|| format!("Failed to parse {}", sidecar.display()))?;
+
+        let dir = sidecar.parent().unwrap_or(Path::new("."));
+        let root = dir.join(spec.root.as_deref().unwrap_or(&name));
+        if !root.is_dir() {
+            bail!(
+                "{}: root {} is not a directory",
+                sidecar.display(),
+                root.display()
+            );
+        }
+        for e in &spec.expect {
+            if !root.join(&e.file).is_file() {
+                bail!(
+                    "{}: expected {} finding in missing file {}",
+                    sidecar.display(),
+                    e.rule,
+                    e.file
+                );
+            }
+        }
+
+        let rules = if spec.rules.is_empty() {
+            spec.expect.iter().map(|e| e.rule.clone()).collect()
+        } else {
+            spec.rules.into_iter().collect()
+        };
+        Ok(Self {
+            name,
+            root,
+            rules,
+            expect: spec.expect,
+        })
+    }
+
+    /// Every corpus in `dir` (`*.toml`), by name
+    pub fn discover(dir: &Path) -> Result<Vec<Self>> {
+        let mut sidecars: Vec<PathBuf> = std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to read corpus directory {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "toml"))
+            .collect();
+        sidecars.sort();
+        sidecars.iter().map(|p| Self::load(p)).collect()
+    }
+
+    /// The config the tree is analyzed with
+    pub fn config(&self) -> Result<RevetConfig> {
+        let path = self.root.join(".revet.toml");
+        if path.is_file() {
+            RevetConfig::from_file(&path)
+                .with_context(|| format!("Failed to load {}", path.display()))
+        } else {
+            Ok(RevetConfig::default())
+        }
+    }
+
+    /// Run the pipeline on the tree and return the scored findings
+    pub fn observe(&self) -> Result<Vec<Observed>> {
+        let analysis = analyze_root(&self.root, &self.config()?)?;
+        Ok(analysis
+            .findings
+            .iter()
+            .filter_map(|f| {
+                let rule = prefix_of(&f.id);
+                if !self.rules.contains(rule) {
+                    return None;
+                }
+                let file = f.file.strip_prefix(&analysis.root).unwrap_or(&f.file);
+                Some(Observed {
+                    file: file.to_string_lossy().replace('\\', "/"),
+                    rule: rule.to_string(),
+                    line: f.line,
+                    message: f.message.clone(),
+                })
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,18 @@
+//! Revet evaluation harness — per-rule precision and recall over annotated
+//! fixture corpora
+//!
+//! A corpus is a directory of fixture files plus a sidecar TOML declaring the
+//! findings revet should report on it (see [`corpus`]). The harness runs the
+//! in-process pipeline ([`revet_core::analyze_root`]) on every corpus, matches
+//! findings to the declared ones ([`metrics`]) and compares the per-rule
+//! counts with a committed baseline ([`baseline`]), so an analyzer change that
+//! loses true positives or adds false positives shows up as a diff in review
+//! and fails `revet-eval --check` in CI.
+
+pub mod baseline;
+pub mod corpus;
+pub mod metrics;
+
+pub use baseline::{Baseline, Regression};
+pub use corpus::{Corpus, Expectation};
+pub use metrics::{Miss, MissKind, Observed, Report, RuleMetrics};
@@ -0,0 +1,169 @@
+//! revet-eval — score Revet analyzers on the annotated corpora in `eval/`
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use colored::Colorize;
+use revet_eval::{Baseline, Corpus, MissKind, Report, RuleMetrics};
+use std::path::PathBuf;
+
+const DEFAULT_CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../eval/corpus");
+const DEFAULT_BASELINE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../eval/baseline.toml");
+
+#[derive(Parser)]
+#[command(name = "revet-eval")]
+#[command(about = "Per-rule precision and recall of Revet analyzers on annotated corpora")]
+struct Args {
+    /// Directory of corpus sidecar files (`<name>.toml`)
+    #[arg(long, default_value = DEFAULT_CORPUS)]
+    corpus: PathBuf,
+
+    /// Baseline metrics file
+    #[arg(long, default_value = DEFAULT_BASELINE)]
+    baseline: PathBuf,
+
+    /// Only run the named corpora
+    #[arg(long = "only", value_name = "NAME")]
+    only: Vec<String>,
+
+    /// Exit with code 1 when a rule regresses against the baseline
+    #[arg(long, conflicts_with = "update")]
+    check: bool,
+
+    /// Rewrite the baseline with the current metrics
+    #[arg(long)]
+    update: bool,
+
+    /// List every false positive and false negative
+    #[arg(long)]
+    details: bool,
+
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let mut corpora = Corpus::discover(&args.corpus)?;
+    if !args.only.is_empty() {
+        corpora.retain(|c| args.only.contains(&c.name));
+    }
+    if corpora.is_empty() {
+        bail!("No corpora found in {}", args.corpus.display());
+    }
+
+    let mut report = Report::default();
+    for corpus in &corpora {
+        let observed = corpus.observe()?;
+        report.merge(Report::score(&corpus.name, &corpus.expect, &observed));
+    }
+
+    let mut baseline = Baseline::load(&args.baseline)?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_table(&report, &baseline, corpora.len());
+        if args.details {
+            print_details(&report);
+        }
+    }
+
+    if args.update {
+        baseline.update(&report);
+        baseline.save(&args.baseline)?;
+        eprintln!("  {} {}", "Updated".green(), args.baseline.display());
+        return Ok(());
+    }
+
+    if args.check {
+        let regressions = baseline.check(&report);
+        if !regressions.is_empty() {
+            eprintln!("\n  {}", "Regressions against the baseline:".red().bold());
+            for r in &regressions {
+                eprintln!("    {} {}", "✗".red(), r);
+            }
+            eprintln!(
+                "\n  Run with {} to see the affected findings, or {} if the change is intended.",
+                "--details".cyan(),
+                "--update".cyan()
+            );
+            std::process::exit(1);
+        }
+        eprintln!("\n  {} no regressions", "✓".green());
+    }
+    Ok(())
+}
+
+fn print_table(report: &Report, baseline: &Baseline, corpora: usize) {
+    println!("\n  {} ({} corpora)\n", "Revet evaluation".bold(), corpora);
+    println!(
+        "  {:<8} {:>4} {:>4} {:>4}  {:>16}  {:>16}",
+        "rule", "TP", "FP", "FN", "precision", "recall"
+    );
+    for (rule, m) in &report.rules {
+        print_row(rule, m, baseline.get(rule));
+    }
+    println!("  {}", "─".repeat(60).dimmed());
+    let total = report.total();
+    let base_total = (!baseline.rules.is_empty()).then(|| {
+        baseline
+            .rules
+            .values()
+            .fold(RuleMetrics::default(), |mut acc, b| {
+                acc.true_positives += b.true_positives;
+                acc.false_positives += b.false_positives;
+                acc.false_negatives += b.false_negatives;
+                acc
+            })
+    });
+    print_row("total", &total, base_total);
+}
+
+fn print_row(rule: &str, m: &RuleMetrics, base: Option<RuleMetrics>) {
+    let (precision, recall) = match base {
+        Some(b) => (
+            with_delta(m.precision(), b.precision()),
+            with_delta(m.recall(), b.recall()),
+        ),
+        None => (
+            format!("{:>6.1}%   (new)", m.precision() * 100.0),
+            format!("{:>6.1}%   (new)", m.recall() * 100.0),
+        ),
+    };
+    println!(
+        "  {:<8} {:>4} {:>4} {:>4}  {:>16}  {:>16}",
+        rule, m.true_positives, m.false_positives, m.false_negatives, precision, recall
+    );
+}
+
+fn with_delta(now: f64, was: f64) -> String {
+    let delta = (now - was) * 100.0;
+    let value = format!("{:>6.1}%", now * 100.0);
+    if delta.abs() < 0.05 {
+        format!("{value}         ")
+    } else if delta > 0.0 {
+        format!("{value} {}", format!("{:>+7.1}", delta).green())
+    } else {
+        format!("{value} {}", format!("{:>+7.1}", delta).red())
+    }
+}
+
+fn print_details(report: &Report) {
+    if report.misses.is_empty() {
+        return;
+    }
+    println!();
+    for m in &report.misses {
+        let tag = match m.kind {
+            MissKind::FalsePositive => "FP".yellow(),
+            MissKind::FalseNegative => "FN".red(),
+        };
+        let location = format!("{:<4} {}/{}:{}", m.rule, m.corpus, m.file, m.line);
+        match &m.message {
+            Some(msg) => println!("  {} {}  {}", tag, location, msg.dimmed()),
+            None => println!("  {} {}", tag, location),
+        }
+    }
+}
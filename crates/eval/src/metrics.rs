@@ -0,0 +1,167 @@
+//! Matching findings to expectations and per-rule counts
+
+use crate::corpus::Expectation;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A scored finding, reduced to what matching needs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Observed {
+    /// Path relative to the corpus root, `/`-separated
+    pub file: String,
+    /// Finding prefix
+    pub rule: String,
+    pub line: usize,
+    pub message: String,
+}
+
+/// True/false positive and false negative counts for one finding prefix
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleMetrics {
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub false_negatives: usize,
+}
+
+impl RuleMetrics {
+    /// TP / (TP + FP); 1.0 when the rule reported nothing
+    pub fn precision(&self) -> f64 {
+        ratio(
+            self.true_positives,
+            self.true_positives + self.false_positives,
+        )
+    }
+
+    /// TP / (TP + FN); 1.0 when nothing was expected
+    pub fn recall(&self) -> f64 {
+        ratio(
+            self.true_positives,
+            self.true_positives + self.false_negatives,
+        )
+    }
+
+    fn add(&mut self, other: &RuleMetrics) {
+        self.true_positives += other.true_positives;
+        self.false_positives += other.false_positives;
+        self.false_negatives += other.false_negatives;
+    }
+}
+
+fn ratio(num: usize, den: usize) -> f64 {
+    if den == 0 {
+        1.0
+    } else {
+        num as f64 / den as f64
+    }
+}
+
+/// Why an entry ended up in [`Report::misses`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissKind {
+    /// Reported but not expected
+    FalsePositive,
+    /// Expected but not reported
+    FalseNegative,
+}
+
+/// One unmatched finding or expectation, for `--details`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Miss {
+    pub kind: MissKind,
+    pub corpus: String,
+    pub file: String,
+    pub rule: String,
+    pub line: usize,
+    /// Finding message (false positives only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Per-rule counts over one or more corpora
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Report {
+    pub rules: BTreeMap<String, RuleMetrics>,
+    pub misses: Vec<Miss>,
+}
+
+impl Report {
+    /// Score the findings of `corpus` against its expectations
+    ///
+    /// Each expectation claims the closest unclaimed finding with the same
+    /// file and prefix whose line is within tolerance; expectations are
+    /// processed tightest-tolerance first so a wide window cannot steal the
+    /// finding an exact one needs. Unclaimed findings are false positives.
+    pub fn score(corpus: &str, expect: &[Expectation], observed: &[Observed]) -> Self {
+        let mut report = Self::default();
+        let mut claimed = vec![false; observed.len()];
+
+        let mut order: Vec<&Expectation> = expect.iter().collect();
+        order.sort_by_key(|e| (e.tolerance, e.file.as_str(), e.line));
+        for e in order {
+            let best = observed
+                .iter()
+                .enumerate()
+                .filter(|(i, o)| {
+                    !claimed[*i] && o.file == e.file && o.rule == e.rule && e.accepts(o.line)
+                })
+                .min_by_key(|(i, o)| (o.line.abs_diff(e.line), *i))
+                .map(|(i, _)| i);
+            let metrics = report.rules.entry(e.rule.clone()).or_default();
+            match best {
+                Some(i) => {
+                    claimed[i] = true;
+                    metrics.true_positives += 1;
+                }
+                None => {
+                    metrics.false_negatives += 1;
+                    report.misses.push(Miss {
+                        kind: MissKind::FalseNegative,
+                        corpus: corpus.to_string(),
+                        file: e.file.clone(),
+                        rule: e.rule.clone(),
+                        line: e.line,
+                        message: None,
+                    });
+                }
+            }
+        }
+
+        for (o, _) in observed.iter().zip(&claimed).filter(|(_, c)| !**c) {
+            report
+                .rules
+                .entry(o.rule.clone())
+                .or_default()
+                .false_positives += 1;
+            report.misses.push(Miss {
+                kind: MissKind::FalsePositive,
+                corpus: corpus.to_string(),
+                file: o.file.clone(),
+                rule: o.rule.clone(),
+                line: o.line,
+                message: Some(o.message.clone()),
+            });
+        }
+        report
+            .misses
+            .sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        report
+    }
+
+    /// Add the counts and misses of `other` to `self`
+    pub fn merge(&mut self, other: Report) {
+        for (rule, m) in &other.rules {
+            self.rules.entry(rule.clone()).or_default().add(m);
+        }
+        self.misses.extend(other.misses);
+    }
+
+    /// Sum over all rules
+    pub fn total(&self) -> RuleMetrics {
+        let mut total = RuleMetrics::default();
+        for m in self.rules.values() {
+            total.add(m);
+        }
+        total
+    }
+}
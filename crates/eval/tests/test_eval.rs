@@ -0,0 +1,301 @@
+//! Tests for the evaluation harness: corpus loading, matching and baselines
+
+use revet_eval::{Baseline, Corpus, Expectation, MissKind, Observed, Report};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn expect(file: &str, rule: &str, line: usize, tolerance: usize) -> Expectation {
+    Expectation {
+        file: file.to_string(),
+        rule: rule.to_string(),
+        line,
+        tolerance,
+    }
+}
+
+fn observed(file: &str, rule: &str, line: usize) -> Observed {
+    Observed {
+        file: file.to_string(),
+        rule: rule.to_string(),
+        line,
+        message: format!("{rule} at {line}"),
+    }
+}
+
+fn write(dir: &Path, name: &str, content: &str) {
+    let path = dir.join(name);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+// ── Matching ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_exact_match_is_true_positive() {
+    let report = Report::score(
+        "c",
+        &[expect("a.py", "SQL", 5, 0)],
+        &[observed("a.py", "SQL", 5)],
+    );
+    let m = report.rules["SQL"];
+    assert_eq!(
+        (m.true_positives, m.false_positives, m.false_negatives),
+        (1, 0, 0)
+    );
+    assert!(report.misses.is_empty());
+}
+
+#[test]
+fn test_tolerance_window() {
+    let exp = [expect("a.py", "SQL", 10, 1)];
+    let report = Report::score("c", &exp, &[observed("a.py", "SQL", 11)]);
+    assert_eq!(report.rules["SQL"].true_positives, 1);
+
+    let report = Report::score("c", &exp, &[observed("a.py", "SQL", 12)]);
+    let m = report.rules["SQL"];
+    assert_eq!((m.false_positives, m.false_negatives), (1, 1));
+}
+
+#[test]
+fn test_file_and_rule_must_match() {
+    let report = Report::score(
+        "c",
+        &[expect("a.py", "SQL", 5, 0)],
+        &[observed("b.py", "SQL", 5), observed("a.py", "SEC", 5)],
+    );
+    assert_eq!(report.rules["SQL"].false_negatives, 1);
+    assert_eq!(report.rules["SQL"].false_positives, 1);
+    assert_eq!(report.rules["SEC"].false_positives, 1);
+}
+
+#[test]
+fn test_each_finding_matches_one_expectation() {
+    // Two findings on one line satisfy two expectations; a third is missed
+    let exp = [
+        expect("a.py", "CMPLX", 4, 0),
+        expect("a.py", "CMPLX", 4, 0),
+        expect("a.py", "CMPLX", 4, 0),
+    ];
+    let obs = [observed("a.py", "CMPLX", 4), observed("a.py", "CMPLX", 4)];
+    let m = Report::score("c", &exp, &obs).rules["CMPLX"];
+    assert_eq!((m.true_positives, m.false_negatives), (2, 1));
+}
+
+#[test]
+fn test_exact_expectation_keeps_its_finding_from_wide_window() {
+    // The wide expectation is declared first but must not take line 10
+    let exp = [expect("a.py", "SEC", 9, 2), expect("a.py", "SEC", 10, 0)];
+    let obs = [observed("a.py", "SEC", 10), observed("a.py", "SEC", 8)];
+    let m = Report::score("c", &exp, &obs).rules["SEC"];
+    assert_eq!((m.true_positives, m.false_positives), (2, 0));
+}
+
+#[test]
+fn test_misses_record_kind_and_message() {
+    let report = Report::score(
+        "corpus",
+        &[expect("a.py", "SEC", 3, 0)],
+        &[observed("a.py", "SEC", 7)],
+    );
+    assert_eq!(report.misses.len(), 2);
+    assert_eq!(report.misses[0].kind, MissKind::FalseNegative);
+    assert_eq!(report.misses[0].message, None);
+    assert_eq!(report.misses[1].kind, MissKind::FalsePositive);
+    assert_eq!(report.misses[1].message.as_deref(), Some("SEC at 7"));
+    assert_eq!(report.misses[1].corpus, "corpus");
+}
+
+#[test]
+fn test_precision_and_recall() {
+    let report = Report::score(
+        "c",
+        &[
+            expect("a.py", "SEC", 1, 0),
+            expect("a.py", "SEC", 2, 0),
+            expect("a.py", "SEC", 3, 0),
+            expect("a.py", "SEC", 4, 0),
+        ],
+        &[
+            observed("a.py", "SEC", 1),
+            observed("a.py", "SEC", 2),
+            observed("a.py", "SEC", 3),
+            observed("a.py", "SEC", 9),
+        ],
+    );
+    let m = report.rules["SEC"];
+    assert!((m.precision() - 0.75).abs() < 1e-9);
+    assert!((m.recall() - 0.75).abs() < 1e-9);
+
+    // Empty denominators count as perfect
+    let empty = Report::score("c", &[], &[]);
+    assert_eq!(empty.total().precision(), 1.0);
+    assert_eq!(empty.total().recall(), 1.0);
+}
+
+#[test]
+fn test_merge_sums_counts() {
+    let mut report = Report::score("a", &[expect("x", "SEC", 1, 0)], &[]);
+    report.merge(Report::score("b", &[], &[observed("y", "SEC", 1)]));
+    let m = report.rules["SEC"];
+    assert_eq!((m.false_positives, m.false_negatives), (1, 1));
+    assert_eq!(report.misses.len(), 2);
+}
+
+// ── Baseline ────────────────────────────────────────────────────────────
+
+fn sample_report() -> Report {
+    Report::score(
+        "c",
+        &[expect("a.py", "SEC", 1, 0), expect("a.py", "SEC", 2, 0)],
+        &[observed("a.py", "SEC", 1), observed("a.py", "SEC", 2)],
+    )
+}
+
+#[test]
+fn test_check_passes_against_own_baseline() {
+    let mut baseline = Baseline::default();
+    baseline.update(&sample_report());
+    assert!(baseline.check(&sample_report()).is_empty());
+}
+
+#[test]
+fn test_check_flags_recall_drop_and_new_false_positive() {
+    let mut baseline = Baseline::default();
+    baseline.update(&sample_report());
+
+    let worse = Report::score(
+        "c",
+        &[expect("a.py", "SEC", 1, 0), expect("a.py", "SEC", 2, 0)],
+        &[observed("a.py", "SEC", 1), observed("a.py", "SEC", 5)],
+    );
+    let regressions = baseline.check(&worse);
+    assert_eq!(regressions.len(), 2, "{regressions:?}");
+    assert!(regressions[0]
+        .message
+        .contains("recall dropped from 100.0% to 50.0%"));
+    assert!(regressions[1]
+        .message
+        .contains("false positives rose from 0 to 1"));
+    assert_eq!(regressions[0].to_string().split(':').next(), Some("SEC"));
+}
+
+#[test]
+fn test_check_honours_tolerances() {
+    let mut baseline: Baseline = toml::from_str(
+        "[tolerance]\nfalse_positive_increase = 1\n\n[rules.SEC]\ntrue_positives = 0\nfalse_positives = 0\nfalse_negatives = 0\nrecall_drop = 0.5\n",
+    )
+    .unwrap();
+    baseline.update(&sample_report());
+    assert_eq!(baseline.rules["SEC"].recall_drop, Some(0.5));
+
+    let worse = Report::score(
+        "c",
+        &[expect("a.py", "SEC", 1, 0), expect("a.py", "SEC", 2, 0)],
+        &[observed("a.py", "SEC", 1), observed("a.py", "SEC", 5)],
+    );
+    assert!(baseline.check(&worse).is_empty());
+}
+
+#[test]
+fn test_check_requires_new_rules_in_baseline() {
+    let regressions = Baseline::default().check(&sample_report());
+    assert_eq!(regressions.len(), 1);
+    assert!(regressions[0].message.contains("--update"));
+}
+
+#[test]
+fn test_baseline_round_trips_through_file() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("baseline.toml");
+    assert_eq!(Baseline::load(&path).unwrap(), Baseline::default());
+
+    let mut baseline = Baseline::default();
+    baseline.update(&sample_report());
+    baseline.save(&path).unwrap();
+    assert_eq!(Baseline::load(&path).unwrap(), baseline);
+}
+
+// ── Corpus ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_corpus_end_to_end() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "demo/app.py",
+        "def f(cursor, x):\n    cursor.execute(f\"SELECT * FROM t WHERE id = {x}\")\n",
+    );
+    write(
+        dir.path(),
+        "demo/safe.py",
+        "def g(cursor, x):\n    cursor.execute(\"SELECT * FROM t WHERE id = %s\", (x,))\n",
+    );
+    write(
+        dir.path(),
+        "demo.toml",
+        "[[expect]]\nfile = \"app.py\"\nrule = \"SQL\"\nline = 2\n",
+    );
+
+    let corpora = Corpus::discover(dir.path()).unwrap();
+    assert_eq!(corpora.len(), 1);
+    let corpus = &corpora[0];
+    assert_eq!(corpus.name, "demo");
+    assert!(
+        corpus.rules.contains("SQL"),
+        "rules default to expected prefixes"
+    );
+
+    let observed = corpus.observe().unwrap();
+    let report = Report::score(&corpus.name, &corpus.expect, &observed);
+    let m = report.rules["SQL"];
+    assert_eq!(
+        (m.true_positives, m.false_positives, m.false_negatives),
+        (1, 0, 0),
+        "{:?}",
+        report.misses
+    );
+}
+
+#[test]
+fn test_corpus_uses_its_own_config() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "demo/.revet.toml",
+        "[modules]\nsecurity = false\n",
+    );
+    write(
+        dir.path(),
+        "demo/a.py",
+        "password = \"hunter2-but-longer\"\n",
+    );
+    write(dir.path(), "demo.toml", "rules = [\"SEC\"]\n");
+
+    let corpus = Corpus::load(&dir.path().join("demo.toml")).unwrap();
+    assert!(corpus.observe().unwrap().is_empty());
+}
+
+#[test]
+fn test_corpus_rejects_missing_files_and_unknown_keys() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "demo/a.py", "x = 1\n");
+
+    write(
+        dir.path(),
+        "demo.toml",
+        "[[expect]]\nfile = \"b.py\"\nrule = \"SEC\"\nline = 1\n",
+    );
+    let err = Corpus::load(&dir.path().join("demo.toml")).unwrap_err();
+    assert!(err.to_string().contains("missing file b.py"), "{err}");
+
+    write(dir.path(), "demo.toml", "rulez = [\"SEC\"]\n");
+    assert!(Corpus::load(&dir.path().join("demo.toml")).is_err());
+}
+
+#[test]
+fn test_committed_corpora_load() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../eval/corpus");
+    let corpora = Corpus::discover(&dir).unwrap();
+    assert!(corpora.len() >= 6);
+    assert!(corpora.iter().all(|c| !c.expect.is_empty()));
+}
@@ -42,6 +42,42 @@ export interface GraphStats {
   parseErrors: number;
 }
 
+export interface AiEstimateOptions {
+  /** Budget in USD; defaults to `[ai].max_cost_per_run`. */
+  maxCost?: number;
+}
+
+export interface AiBatch {
+  findings: number;
+  inputTokens: number;
+  outputTokens: number;
+}
+
+export interface AiProvider {
+  provider: string;
+  model: string;
+  costUsd: number;
+}
+
+export interface AiEstimateResult {
+  /** Eligible findings, including those skipped for budget. */
+  eligible: number;
+  requests: number;
+  batches: AiBatch[];
+  inputTokens: number;
+  outputTokens: number;
+  /** Cost on each provider, in the order they are tried. */
+  providers: AiProvider[];
+  estimatedCost: number;
+  maxCost: number;
+  /** Whether `maxCost` could not cover every eligible finding. */
+  exceedsMaxCost: boolean;
+  /** Eligible findings left out to stay within `maxCost`. */
+  skippedBudget: number;
+  /** Lowest priority score that was sent, when findings were skipped. */
+  scoreCutoff?: number;
+}
+
 // ── Async API ─────────────────────────────────────────────────────────────────
 
 /**
@@ -69,6 +105,37 @@ export function analyzeFiles(
  */
 export function analyzeGraph(repoPath: string): Promise<GraphStats>;
 
+/**
+ * Estimate what `revet review --ai --full` would cost, without calling any LLM API.
+ * Builds the same requests a real run would send.
+ */
+export function aiEstimate(
+  repoPath: string,
+  options?: AiEstimateOptions,
+): Promise<AiEstimateResult>;
+
+/** Warm repository graph returned by `openRepository`. Opaque. */
+export class RepositoryHandle {
+  private constructor();
+}
+
+/**
+ * Parse a repository once and keep its graph warm for `analyzeBuffer`.
+ * Uses the incremental parser with on-disk cache (`.revet-cache/`).
+ */
+export function openRepository(repoPath: string): Promise<RepositoryHandle>;
+
+/**
+ * Analyze unsaved editor contents for one file against a warm graph.
+ * `path` may be absolute or relative to the repository root. Nothing is
+ * written to disk or to the cache.
+ */
+export function analyzeBuffer(
+  handle: RepositoryHandle,
+  path: string,
+  text: string,
+): Promise<AnalyzeResult>;
+
 /**
  * Add a finding ID to `[ignore].findings` in `.revet.toml`.
  * Returns `true` if added, `false` if already present (idempotent).
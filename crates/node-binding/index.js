@@ -86,6 +86,9 @@ const native = loadNative();
 exports.analyzeRepository = native.analyzeRepository;
 exports.analyzeFiles      = native.analyzeFiles;
 exports.analyzeGraph      = native.analyzeGraph;
+exports.aiEstimate        = native.aiEstimate;
+exports.openRepository    = native.openRepository;
+exports.analyzeBuffer     = native.analyzeBuffer;
 exports.suppress          = native.suppress;
 exports.getVersion        = native.getVersion;
 
@@ -7,7 +7,7 @@
 //! # JavaScript API
 //!
 //! ```js
-//! const { analyzeRepository, analyzeFiles, analyzeGraph, suppress, getVersion, watch } = require('./index');
+//! const { analyzeRepository, analyzeFiles, analyzeGraph, aiEstimate, openRepository, analyzeBuffer, suppress, getVersion, watch } = require('./index');
 //!
 //! // Full repository scan
 //! const result = await analyzeRepository('/path/to/repo');
@@ -21,6 +21,14 @@
 //! const stats = await analyzeGraph('/path/to/repo');
 //! console.log(stats.nodeCount, stats.edgeCount);
 //!
+//! // What `revet review --ai` would cost, without calling the API
+//! const estimate = await aiEstimate('/path/to/repo', { maxCost: 0.5 });
+//! console.log(estimate.requests, estimate.estimatedCost, estimate.exceedsMaxCost);
+//!
+//! // Unsaved editor buffer (graph stays warm across calls)
+//! const repo = await openRepository('/path/to/repo');
+//! const live = await analyzeBuffer(repo, 'src/auth.ts', editor.getText());
+//!
 //! // Suppress a finding in .revet.toml
 //! const added = await suppress('SEC-001', '/path/to/repo');
 //!
@@ -49,8 +57,16 @@ use napi::bindgen_prelude::*;
 use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
 use napi_derive::napi;
 use revet_core::{
-    analyzer::AnalyzerDispatcher, cache::FileGraphCache, config::RevetConfig,
-    discovery::discover_files_extended, finding::Severity, parser::ParserDispatcher,
+    analyzer::AnalyzerDispatcher,
+    buffer::GraphContext,
+    cache::FileGraphCache,
+    config::RevetConfig,
+    discovery::{discover_files_extended, discover_files_with_dotfiles},
+    finding::Severity,
+    generated::GeneratedFiles,
+    parser::ParserDispatcher,
+    pipeline::analyze_root,
+    storage::{StoragePaths, LEGACY_DIR},
 };
 use std::path::PathBuf;
 use std::sync::{
@@ -114,6 +130,50 @@ pub struct GraphStats {
     pub parse_errors: u32,
 }
 
+/// Options for `aiEstimate`.
+#[napi(object)]
+pub struct AiEstimateOptions {
+    /// Budget in USD; defaults to `[ai].max_cost_per_run`.
+    pub max_cost: Option<f64>,
+}
+
+/// Estimated tokens for one AI request.
+#[napi(object)]
+pub struct JsAiBatch {
+    pub findings: u32,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+}
+
+/// What the AI run would cost on one provider.
+#[napi(object)]
+pub struct JsAiProvider {
+    pub provider: String,
+    pub model: String,
+    pub cost_usd: f64,
+}
+
+/// Result of `aiEstimate`: the requests `revet review --ai` would make.
+#[napi(object)]
+pub struct AiEstimateResult {
+    /// Eligible findings, including those skipped for budget.
+    pub eligible: u32,
+    pub requests: u32,
+    pub batches: Vec<JsAiBatch>,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    /// Cost on each provider, in the order they are tried.
+    pub providers: Vec<JsAiProvider>,
+    pub estimated_cost: f64,
+    pub max_cost: f64,
+    /// Whether `maxCost` could not cover every eligible finding.
+    pub exceeds_max_cost: bool,
+    /// Eligible findings left out to stay within `maxCost`.
+    pub skipped_budget: u32,
+    /// Lowest priority score that was sent, when findings were skipped.
+    pub score_cutoff: Option<f64>,
+}
+
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
 fn to_js_findings(
@@ -202,6 +262,7 @@ fn run_full_analysis(path: &str) -> napi::Result<AnalyzeResult> {
     let parser_exts: Vec<&str> = parser_dispatcher.supported_extensions();
     let extra_exts: Vec<&str> = analyzer_dispatcher.extra_extensions(&config);
     let extra_names: Vec<&str> = analyzer_dispatcher.extra_filenames(&config);
+    let extra_dotfiles: Vec<&str> = analyzer_dispatcher.extra_dotfiles(&config);
 
     let mut all_extensions: Vec<&str> = parser_exts;
     for ext in &extra_exts {
@@ -210,16 +271,23 @@ fn run_full_analysis(path: &str) -> napi::Result<AnalyzeResult> {
         }
     }
 
-    let files = discover_files_extended(
+    let files = discover_files_with_dotfiles(
         &repo_path,
         &all_extensions,
         &extra_names,
+        &extra_dotfiles,
         &config.ignore.paths,
     )
     .map_err(|e| napi::Error::from_reason(format!("File discovery failed: {}", e)))?;
 
     let files_scanned = files.len() as u32;
-    let findings = analyzer_dispatcher.run_all_parallel(&files, &repo_path, &config);
+    let generated = GeneratedFiles::from_config(&config.files).classify(&files);
+    let findings = analyzer_dispatcher.run_all_parallel(
+        &generated.analysis_files(&files),
+        &repo_path,
+        &config,
+    );
+    let findings = generated.apply(findings, &repo_path);
 
     Ok(AnalyzeResult {
         findings: to_js_findings(&findings, &repo_path),
@@ -270,7 +338,13 @@ fn run_files_analysis(files: &[String], root: &str) -> napi::Result<AnalyzeResul
     let paths: Vec<PathBuf> = files.iter().map(PathBuf::from).collect();
 
     let files_scanned = paths.len() as u32;
-    let findings = analyzer_dispatcher.run_all_parallel(&paths, &repo_path, &config);
+    let generated = GeneratedFiles::from_config(&config.files).classify(&paths);
+    let findings = analyzer_dispatcher.run_all_parallel(
+        &generated.analysis_files(&paths),
+        &repo_path,
+        &config,
+    );
+    let findings = generated.apply(findings, &repo_path);
 
     Ok(AnalyzeResult {
         findings: to_js_findings(&findings, &repo_path),
@@ -326,8 +400,7 @@ fn run_graph_analysis(path: &str) -> napi::Result<GraphStats> {
 
     let files_scanned = files.len() as u32;
 
-    let cache_dir = repo_path.join(".revet-cache");
-    let file_cache = FileGraphCache::new(&cache_dir);
+    let file_cache = FileGraphCache::at(&StoragePaths::resolve(&repo_path, &config));
 
     let (graph, errors, _cached, _parsed) =
         parser_dispatcher.parse_files_incremental(&files, repo_path, &file_cache);
@@ -345,7 +418,8 @@ fn run_graph_analysis(path: &str) -> napi::Result<GraphStats> {
 
 /// Parse the repository and return code graph statistics.
 ///
-/// Uses the incremental parser with on-disk cache (`.revet-cache/`) for speed.
+/// Uses the incremental parser with its on-disk cache (`.revet-cache/` unless
+/// relocated with `REVET_DATA_DIR` or `[storage] data_dir`) for speed.
 /// Returns node/edge counts useful for dependency analysis dashboards.
 ///
 /// @param repoPath - Absolute or relative path to the repository root.
@@ -354,6 +428,88 @@ pub fn analyze_graph(repo_path: String) -> AsyncTask<AnalyzeGraphTask> {
     AsyncTask::new(AnalyzeGraphTask { repo_path })
 }
 
+// ── aiEstimate ────────────────────────────────────────────────────────────────
+
+pub struct AiEstimateTask {
+    repo_path: String,
+    max_cost: Option<f64>,
+}
+
+impl Task for AiEstimateTask {
+    type Output = AiEstimateResult;
+    type JsValue = AiEstimateResult;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        run_ai_estimate(&self.repo_path, self.max_cost)
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+fn run_ai_estimate(path: &str, max_cost: Option<f64>) -> napi::Result<AiEstimateResult> {
+    let repo_path = canonicalize_repo(path)?;
+    let config = RevetConfig::find_and_load(&repo_path).unwrap_or_default();
+    let analysis = analyze_root(&repo_path, &config)
+        .map_err(|e| napi::Error::from_reason(format!("Analysis failed: {}", e)))?;
+
+    let max_cost = max_cost.unwrap_or(config.ai.max_cost_per_run);
+    let estimate = analysis
+        .ai_estimate(&config, max_cost)
+        .map_err(|e| napi::Error::from_reason(format!("Cannot build AI requests: {}", e)))?;
+
+    Ok(AiEstimateResult {
+        eligible: estimate.eligible as u32,
+        requests: estimate.requests as u32,
+        batches: estimate
+            .batches
+            .iter()
+            .map(|b| JsAiBatch {
+                findings: b.findings as u32,
+                input_tokens: b.input_tokens as u32,
+                output_tokens: b.output_tokens as u32,
+            })
+            .collect(),
+        input_tokens: estimate.input_tokens as u32,
+        output_tokens: estimate.output_tokens as u32,
+        providers: estimate
+            .providers
+            .into_iter()
+            .map(|p| JsAiProvider {
+                provider: p.provider,
+                model: p.model,
+                cost_usd: p.cost_usd,
+            })
+            .collect(),
+        estimated_cost: estimate.estimated_cost,
+        max_cost: estimate.max_cost,
+        exceeds_max_cost: estimate.exceeds_max_cost,
+        skipped_budget: estimate.skipped_budget as u32,
+        score_cutoff: estimate.score_cutoff,
+    })
+}
+
+/// Estimate what `revet review --ai --full` would cost, without calling any
+/// LLM API.
+///
+/// Runs the analyzers, selects the findings AI enrichment would send and
+/// builds the same requests a real run would. Config (including `[ai]`) is
+/// loaded from `.revet.toml` in the repository root (or defaults).
+///
+/// @param repoPath - Absolute or relative path to the repository root.
+/// @param options  - Optional `maxCost` overriding `[ai].max_cost_per_run`.
+#[napi(js_name = "aiEstimate")]
+pub fn ai_estimate(
+    repo_path: String,
+    options: Option<AiEstimateOptions>,
+) -> AsyncTask<AiEstimateTask> {
+    AsyncTask::new(AiEstimateTask {
+        repo_path,
+        max_cost: options.and_then(|o| o.max_cost),
+    })
+}
+
 // ── suppress ─────────────────────────────────────────────────────────────────
 
 pub struct SuppressTask {
@@ -438,6 +594,108 @@ pub fn suppress_finding(finding_id: String, repo_path: String) -> AsyncTask<Supp
     })
 }
 
+// ── openRepository / analyzeBuffer ───────────────────────────────────────────
+
+/// Warm repository graph returned by `openRepository`, consumed by
+/// `analyzeBuffer`. Dropping the handle frees the graph.
+#[napi]
+pub struct RepositoryHandle {
+    repo_path: PathBuf,
+    config: Arc<RevetConfig>,
+    ctx: Arc<GraphContext>,
+}
+
+pub struct OpenRepositoryTask {
+    repo_path: String,
+}
+
+impl Task for OpenRepositoryTask {
+    type Output = RepositoryHandle;
+    type JsValue = RepositoryHandle;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        let repo_path = canonicalize_repo(&self.repo_path)?;
+        let config = RevetConfig::find_and_load(&repo_path).unwrap_or_default();
+        // Buffer analyses reuse the findings of unchanged content
+        revet_core::AnalysisMemo::start_session(&config);
+        let ctx = GraphContext::load(&repo_path, &config)
+            .map_err(|e| napi::Error::from_reason(format!("Graph build failed: {}", e)))?;
+        Ok(RepositoryHandle {
+            repo_path,
+            config: Arc::new(config),
+            ctx: Arc::new(ctx),
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Parse a repository once and keep its graph warm for `analyzeBuffer`.
+///
+/// Uses the incremental parser with its on-disk cache.
+///
+/// @param repoPath - Absolute or relative path to the repository root.
+#[napi(js_name = "openRepository")]
+pub fn open_repository(repo_path: String) -> AsyncTask<OpenRepositoryTask> {
+    AsyncTask::new(OpenRepositoryTask { repo_path })
+}
+
+pub struct AnalyzeBufferTask {
+    repo_path: PathBuf,
+    config: Arc<RevetConfig>,
+    ctx: Arc<GraphContext>,
+    path: String,
+    text: String,
+}
+
+impl Task for AnalyzeBufferTask {
+    type Output = AnalyzeResult;
+    type JsValue = AnalyzeResult;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        let findings = revet_core::analyze_buffer(
+            std::path::Path::new(&self.path),
+            &self.text,
+            &self.config,
+            &self.ctx,
+        );
+        Ok(AnalyzeResult {
+            findings: to_js_findings(&findings, &self.repo_path),
+            summary: summarize(&findings, 1),
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Analyze unsaved editor contents for one file.
+///
+/// Runs domain analyzers and file-local graph checks on `text` as if it were
+/// the contents of `path`, using the handle's graph for cross-file context.
+/// Nothing is written to disk or to the cache.
+///
+/// @param handle - Handle from `openRepository`.
+/// @param path   - File path (absolute, or relative to the repository root).
+/// @param text   - Current buffer contents.
+#[napi(js_name = "analyzeBuffer")]
+pub fn analyze_buffer(
+    handle: &RepositoryHandle,
+    path: String,
+    text: String,
+) -> AsyncTask<AnalyzeBufferTask> {
+    AsyncTask::new(AnalyzeBufferTask {
+        repo_path: handle.repo_path.clone(),
+        config: handle.config.clone(),
+        ctx: handle.ctx.clone(),
+        path,
+        text,
+    })
+}
+
 // ── getVersion ────────────────────────────────────────────────────────────────
 
 /// Return the revet-core library version string.
@@ -690,7 +948,7 @@ pub fn watch_repository(
                     // the .revet-cache/ dir to avoid feedback loops.
                     for path in event.paths {
                         if path.is_file()
-                            && !path.components().any(|c| c.as_os_str() == ".revet-cache")
+                            && !path.components().any(|c| c.as_os_str() == LEGACY_DIR)
                             && !pending.contains(&path)
                         {
                             pending.push(path);
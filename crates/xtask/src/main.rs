@@ -0,0 +1,139 @@
+//! cargo xtask — development tasks for the Revet workspace
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use revet_core::parser::{conformance, fuzz};
+use revet_core::ParserDispatcher;
+use std::path::{Path, PathBuf};
+
+const WORKSPACE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../..");
+
+#[derive(Parser)]
+#[command(name = "cargo xtask")]
+#[command(about = "Development tasks for the Revet workspace")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Write a parser conformance fixture and its manifest of expected
+    /// entities
+    GenFixtures {
+        /// Parser language name or file extension (`swift`, `py`), or `all`
+        #[arg(long)]
+        language: String,
+
+        /// Seed; the same seed always writes the same fixture
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Output directory [default: target/fixtures]
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Write the seed corpus of the `parse_source` cargo-fuzz target
+    FuzzCorpus {
+        /// Output directory [default: crates/core/fuzz/corpus/parse_source]
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Rewrite the golden output reports (`crates/cli/tests/golden`) from
+    /// their fixtures, after an intentional output change
+    BlessOutput,
+}
+
+fn main() -> Result<()> {
+    match Args::parse().command {
+        Command::GenFixtures {
+            language,
+            seed,
+            out,
+        } => {
+            let out = out.unwrap_or_else(|| Path::new(WORKSPACE).join("target/fixtures"));
+            let languages: Vec<String> = if language == "all" {
+                conformance::languages().map(str::to_string).collect()
+            } else {
+                vec![language]
+            };
+            for language in &languages {
+                gen_fixture(language, seed, &out)?;
+            }
+            Ok(())
+        }
+        Command::FuzzCorpus { out } => {
+            let out = out.unwrap_or_else(|| {
+                Path::new(WORKSPACE).join("crates/core/fuzz/corpus/parse_source")
+            });
+            fuzz_corpus(&out)
+        }
+        Command::BlessOutput => bless_output(),
+    }
+}
+
+fn gen_fixture(language: &str, seed: u64, out: &Path) -> Result<()> {
+    let Some(fixture) = conformance::generate(language, seed) else {
+        bail!(
+            "No conformance fixtures for `{}` (known: {})",
+            language,
+            conformance::languages().collect::<Vec<_>>().join(", ")
+        );
+    };
+    std::fs::create_dir_all(out).with_context(|| format!("Failed to create {}", out.display()))?;
+    let source = out.join(&fixture.file_name);
+    std::fs::write(&source, &fixture.source)
+        .with_context(|| format!("Failed to write {}", source.display()))?;
+    let manifest = out.join(format!("{}.manifest.json", fixture.file_name));
+    std::fs::write(&manifest, serde_json::to_string_pretty(&fixture.manifest)?)
+        .with_context(|| format!("Failed to write {}", manifest.display()))?;
+    println!("{}", source.display());
+    println!("{}", manifest.display());
+    Ok(())
+}
+
+/// One file per seed, its first byte selecting the parser as the fuzz
+/// target does: the parser's index among the registered ones
+fn fuzz_corpus(out: &Path) -> Result<()> {
+    std::fs::create_dir_all(out).with_context(|| format!("Failed to create {}", out.display()))?;
+    let dispatcher = ParserDispatcher::new();
+    let mut written = 0;
+    for (index, parser) in dispatcher.parsers().enumerate() {
+        for (n, seed) in fuzz::seeds(Path::new(WORKSPACE), parser)
+            .into_iter()
+            .enumerate()
+        {
+            let mut input = vec![index as u8];
+            input.extend_from_slice(seed.as_bytes());
+            let name = format!("{}-{}", fuzz::file_name(parser).display(), n);
+            std::fs::write(out.join(name), input)?;
+            written += 1;
+        }
+    }
+    println!("{} seeds in {}", written, out.display());
+    Ok(())
+}
+
+/// Run the golden output test with `REVET_BLESS` set, which writes every
+/// report instead of comparing it; `git diff` then shows what changed
+fn bless_output() -> Result<()> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let status = std::process::Command::new(cargo)
+        .args(["test", "-p", "revet", "--test", "test_golden_output"])
+        .env("REVET_BLESS", "1")
+        .current_dir(WORKSPACE)
+        .status()
+        .context("Failed to run cargo test")?;
+    if !status.success() {
+        bail!("The golden output test failed ({})", status);
+    }
+    println!(
+        "Goldens written to {}",
+        Path::new(WORKSPACE)
+            .join("crates/cli/tests/golden")
+            .display()
+    );
+    Ok(())
+}
@@ -11,10 +11,21 @@ const sidebars: SidebarsConfig = {
         'commands/review',
         'commands/diff',
         'commands/baseline',
+        'commands/import',
         'commands/log',
         'commands/watch',
         'commands/init',
         'commands/explain',
+        'commands/fixes',
+        'commands/multi',
+        'commands/ci',
+        'commands/api',
+        'commands/snapshot',
+        'commands/debug',
+        'commands/upgrade-report',
+        'commands/hash-inputs',
+        'commands/calibrate',
+        'commands/issues',
       ],
     },
     {
@@ -25,12 +36,38 @@ const sidebars: SidebarsConfig = {
         'analyzers/security',
         'analyzers/ml-pipeline',
         'analyzers/infrastructure',
+        'analyzers/kubernetes',
+        'analyzers/skew',
+        'analyzers/i18n',
+        'analyzers/feature-flags',
         'analyzers/react-hooks',
         'analyzers/async-patterns',
         'analyzers/dependency',
         'analyzers/error-handling',
         'analyzers/toolchain',
+        'analyzers/magic-numbers',
+        'analyzers/resource-leaks',
+        'analyzers/generated-code',
         'analyzers/custom-rules',
+        'analyzers/suppressions',
+        'analyzers/config-drift',
+        'analyzers/complexity',
+        'analyzers/hotspots',
+        'analyzers/ownership',
+        'analyzers/refactor',
+        'analyzers/debug-artifacts',
+        'analyzers/test-quality',
+        'analyzers/a11y',
+        'analyzers/copy',
+        'analyzers/numeric',
+        'analyzers/upgrade',
+        'analyzers/pagination',
+        'analyzers/websec',
+        'analyzers/contract',
+        'analyzers/repository',
+        'analyzers/coupling',
+        'analyzers/docs',
+        'analyzers/concurrency',
       ],
     },
     'language-parsers',
@@ -0,0 +1,11 @@
+"""Small helpers; none should be reported."""
+
+
+def money(amount):
+    return f"{amount:.2f}"
+
+
+def label(item):
+    if item.discount:
+        return f"{item.name} (sale)"
+    return item.name
@@ -0,0 +1,44 @@
+"""Pricing rules that grew one branch at a time."""
+
+
+def price(order, customer, region, coupon, season, channel):
+    total = 0
+    for item in order.items:
+        if item.kind == "book":
+            if customer.member:
+                if season == "summer":
+                    if region == "eu":
+                        total += item.price * 0.8
+                    else:
+                        total += item.price * 0.85
+                else:
+                    total += item.price * 0.9
+            else:
+                total += item.price
+        elif item.kind == "food":
+            if region == "eu" and channel == "web":
+                total += item.price * 1.07
+            elif region == "us" or channel == "store":
+                total += item.price * 1.05
+            else:
+                total += item.price
+        elif item.kind == "gift":
+            total += 0
+        else:
+            total += item.price
+    if coupon:
+        if coupon.percent:
+            total -= total * coupon.percent
+        elif coupon.amount:
+            total -= coupon.amount
+    return total
+
+
+def subtotal(items):
+    return sum(item.price for item in items)
+
+
+def apply_tax(amount, rate):
+    if rate < 0:
+        raise ValueError("negative rate")
+    return amount * (1 + rate)
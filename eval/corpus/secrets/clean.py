@@ -0,0 +1,16 @@
+"""Settings read from the environment; nothing here is a secret."""
+
+import os
+
+PASSWORD_MIN_LENGTH = 12
+password_field = "password"
+token_header = "Authorization"
+SECRET_KEY = os.environ.get("SECRET_KEY")
+
+
+def login(username, password):
+    return check(username, password)
+
+
+def check(username, password):
+    return bool(username and password)
@@ -0,0 +1,21 @@
+"""Queries built from user input."""
+
+
+def find_user(cursor, username):
+    cursor.execute(f"SELECT * FROM users WHERE name = '{username}'")
+    return cursor.fetchone()
+
+
+def find_order(cursor, order_id):
+    query = "SELECT * FROM orders WHERE id = " + order_id
+    cursor.execute(query)
+    return cursor.fetchone()
+
+
+def delete_user(cursor, user_id):
+    cursor.execute("DELETE FROM users WHERE id = %s" % user_id)
+
+
+def search(cursor, term):
+    cursor.execute("SELECT * FROM items WHERE title LIKE '%{}%'".format(term))
+    return cursor.fetchall()
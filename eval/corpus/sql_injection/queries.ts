@@ -0,0 +1,11 @@
+import { Pool } from "pg";
+
+const pool = new Pool();
+
+export async function findUser(name: string) {
+  return pool.query(`SELECT * FROM users WHERE name = '${name}'`);
+}
+
+export async function findUserSafe(name: string) {
+  return pool.query("SELECT * FROM users WHERE name = $1", [name]);
+}
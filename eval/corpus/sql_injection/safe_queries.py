@@ -0,0 +1,16 @@
+"""Parameterized queries — none of these are injectable."""
+
+
+def find_user(cursor, username):
+    cursor.execute("SELECT * FROM users WHERE name = %s", (username,))
+    return cursor.fetchone()
+
+
+def find_order(cursor, order_id):
+    cursor.execute("SELECT * FROM orders WHERE id = ?", [order_id])
+    return cursor.fetchone()
+
+
+def count_users(cursor):
+    cursor.execute("SELECT COUNT(*) FROM users")
+    return cursor.fetchone()[0]
@@ -0,0 +1,5 @@
+from util import slugify
+
+
+def handler(title):
+    return slugify(title)
@@ -0,0 +1,7 @@
+import { formatDate, parseDate } from "./util";
+
+export function main(): void {
+  console.log(formatDate(parseDate("2024-01-01")));
+}
+
+main();
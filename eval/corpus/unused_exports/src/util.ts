@@ -0,0 +1,13 @@
+export function formatDate(d: Date): string {
+  return d.toISOString().slice(0, 10);
+}
+
+export function parseDate(s: string): Date {
+  return new Date(s);
+}
+
+export function legacyFormat(d: Date): string {
+  return d.toString();
+}
+
+export const DEFAULT_LOCALE = "en";
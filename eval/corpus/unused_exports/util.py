@@ -0,0 +1,6 @@
+def slugify(text):
+    return text.lower().replace(" ", "-")
+
+
+def unused_helper(text):
+    return text.strip()
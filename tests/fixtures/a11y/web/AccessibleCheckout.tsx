@@ -0,0 +1,50 @@
+import { Avatar, FormLabel } from "./ui";
+
+type Props = {
+  imageProps: object;
+  onClose: () => void;
+  onSelect: () => void;
+};
+
+export function AccessibleCheckout({ imageProps, onClose, onSelect }: Props) {
+  return (
+    <section>
+      <img src="/logo.svg" alt="Acme" />
+      <img src="/divider.svg" alt="" />
+      <img src="/sparkle.svg" aria-hidden="true" />
+      <img {...imageProps} />
+      <Avatar src="/me.png" />
+      <button className="close" onClick={onClose}>
+        ×
+      </button>
+      <div role="button" tabIndex={0} onClick={onSelect} onKeyDown={onSelect}>
+        Select
+      </div>
+      <a href="/help" onClick={onClose}>
+        Help
+      </a>
+      <label>
+        Email <input type="email" />
+      </label>
+      <label htmlFor="country">Country</label>
+      <select id="country">
+        <option>NL</option>
+      </select>
+      <FormLabel htmlFor="notes">Notes</FormLabel>
+      <textarea id="notes" />
+      <input type="search" aria-label="Search orders" />
+      <input type="hidden" name="csrf" value="token" />
+      <input type="submit" value="Pay" />
+      <div tabIndex={-1}>Skip target</div>
+      <span tabIndex={0}>Focusable</span>
+    </section>
+  );
+}
+
+export function AccessibleLayout({ locale, children }: { locale: string; children: React.ReactNode }) {
+  return (
+    <html lang={locale}>
+      <body>{children}</body>
+    </html>
+  );
+}
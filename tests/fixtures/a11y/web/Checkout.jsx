@@ -0,0 +1,19 @@
+import React from "react";
+
+export function Checkout({ onClose, onPay }) {
+  return (
+    <section>
+      <img src="/logo.svg" />
+      <div className="close" onClick={onClose}>
+        ×
+      </div>
+      <input type="email" placeholder="Email" />
+      <select id="country">
+        <option>NL</option>
+      </select>
+      <button tabIndex={2} onClick={onPay}>
+        Pay
+      </button>
+    </section>
+  );
+}
@@ -0,0 +1,256 @@
+import csv
+import io
+
+
+def build_report(rows, currency):
+    """Totals per bucket, converted to `currency`"""
+    rate = exchange_rate(currency)
+    total = 0
+    for row in rows:
+        total += row.amount
+    bucket_0 = sum(r.amount for r in rows if r.bucket == 0)
+    bucket_1 = sum(r.amount for r in rows if r.bucket == 1)
+    bucket_2 = sum(r.amount for r in rows if r.bucket == 2)
+    bucket_3 = sum(r.amount for r in rows if r.bucket == 3)
+    bucket_4 = sum(r.amount for r in rows if r.bucket == 4)
+    bucket_5 = sum(r.amount for r in rows if r.bucket == 5)
+    bucket_6 = sum(r.amount for r in rows if r.bucket == 6)
+    bucket_7 = sum(r.amount for r in rows if r.bucket == 7)
+    bucket_8 = sum(r.amount for r in rows if r.bucket == 8)
+    bucket_9 = sum(r.amount for r in rows if r.bucket == 9)
+    bucket_10 = sum(r.amount for r in rows if r.bucket == 10)
+    bucket_11 = sum(r.amount for r in rows if r.bucket == 11)
+    bucket_12 = sum(r.amount for r in rows if r.bucket == 12)
+    bucket_13 = sum(r.amount for r in rows if r.bucket == 13)
+    bucket_14 = sum(r.amount for r in rows if r.bucket == 14)
+    bucket_15 = sum(r.amount for r in rows if r.bucket == 15)
+    bucket_16 = sum(r.amount for r in rows if r.bucket == 16)
+    bucket_17 = sum(r.amount for r in rows if r.bucket == 17)
+    bucket_18 = sum(r.amount for r in rows if r.bucket == 18)
+    bucket_19 = sum(r.amount for r in rows if r.bucket == 19)
+    bucket_20 = sum(r.amount for r in rows if r.bucket == 20)
+    bucket_21 = sum(r.amount for r in rows if r.bucket == 21)
+    bucket_22 = sum(r.amount for r in rows if r.bucket == 22)
+    bucket_23 = sum(r.amount for r in rows if r.bucket == 23)
+    bucket_24 = sum(r.amount for r in rows if r.bucket == 24)
+    bucket_25 = sum(r.amount for r in rows if r.bucket == 25)
+    bucket_26 = sum(r.amount for r in rows if r.bucket == 26)
+    bucket_27 = sum(r.amount for r in rows if r.bucket == 27)
+    bucket_28 = sum(r.amount for r in rows if r.bucket == 28)
+    bucket_29 = sum(r.amount for r in rows if r.bucket == 29)
+    bucket_30 = sum(r.amount for r in rows if r.bucket == 30)
+    bucket_31 = sum(r.amount for r in rows if r.bucket == 31)
+    bucket_32 = sum(r.amount for r in rows if r.bucket == 32)
+    bucket_33 = sum(r.amount for r in rows if r.bucket == 33)
+    bucket_34 = sum(r.amount for r in rows if r.bucket == 34)
+    bucket_35 = sum(r.amount for r in rows if r.bucket == 35)
+    bucket_36 = sum(r.amount for r in rows if r.bucket == 36)
+    bucket_37 = sum(r.amount for r in rows if r.bucket == 37)
+    bucket_38 = sum(r.amount for r in rows if r.bucket == 38)
+    bucket_39 = sum(r.amount for r in rows if r.bucket == 39)
+    bucket_40 = sum(r.amount for r in rows if r.bucket == 40)
+    bucket_41 = sum(r.amount for r in rows if r.bucket == 41)
+    bucket_42 = sum(r.amount for r in rows if r.bucket == 42)
+    bucket_43 = sum(r.amount for r in rows if r.bucket == 43)
+    bucket_44 = sum(r.amount for r in rows if r.bucket == 44)
+    bucket_45 = sum(r.amount for r in rows if r.bucket == 45)
+    bucket_46 = sum(r.amount for r in rows if r.bucket == 46)
+    bucket_47 = sum(r.amount for r in rows if r.bucket == 47)
+    bucket_48 = sum(r.amount for r in rows if r.bucket == 48)
+    bucket_49 = sum(r.amount for r in rows if r.bucket == 49)
+    bucket_50 = sum(r.amount for r in rows if r.bucket == 50)
+    bucket_51 = sum(r.amount for r in rows if r.bucket == 51)
+    bucket_52 = sum(r.amount for r in rows if r.bucket == 52)
+    bucket_53 = sum(r.amount for r in rows if r.bucket == 53)
+    bucket_54 = sum(r.amount for r in rows if r.bucket == 54)
+    bucket_55 = sum(r.amount for r in rows if r.bucket == 55)
+    bucket_56 = sum(r.amount for r in rows if r.bucket == 56)
+    bucket_57 = sum(r.amount for r in rows if r.bucket == 57)
+    bucket_58 = sum(r.amount for r in rows if r.bucket == 58)
+    bucket_59 = sum(r.amount for r in rows if r.bucket == 59)
+    bucket_60 = sum(r.amount for r in rows if r.bucket == 60)
+    bucket_61 = sum(r.amount for r in rows if r.bucket == 61)
+    bucket_62 = sum(r.amount for r in rows if r.bucket == 62)
+    bucket_63 = sum(r.amount for r in rows if r.bucket == 63)
+    bucket_64 = sum(r.amount for r in rows if r.bucket == 64)
+    bucket_65 = sum(r.amount for r in rows if r.bucket == 65)
+    bucket_66 = sum(r.amount for r in rows if r.bucket == 66)
+    bucket_67 = sum(r.amount for r in rows if r.bucket == 67)
+    bucket_68 = sum(r.amount for r in rows if r.bucket == 68)
+    bucket_69 = sum(r.amount for r in rows if r.bucket == 69)
+    bucket_70 = sum(r.amount for r in rows if r.bucket == 70)
+    bucket_71 = sum(r.amount for r in rows if r.bucket == 71)
+    bucket_72 = sum(r.amount for r in rows if r.bucket == 72)
+    bucket_73 = sum(r.amount for r in rows if r.bucket == 73)
+    bucket_74 = sum(r.amount for r in rows if r.bucket == 74)
+    bucket_75 = sum(r.amount for r in rows if r.bucket == 75)
+    bucket_76 = sum(r.amount for r in rows if r.bucket == 76)
+    bucket_77 = sum(r.amount for r in rows if r.bucket == 77)
+    bucket_78 = sum(r.amount for r in rows if r.bucket == 78)
+    bucket_79 = sum(r.amount for r in rows if r.bucket == 79)
+    bucket_80 = sum(r.amount for r in rows if r.bucket == 80)
+    bucket_81 = sum(r.amount for r in rows if r.bucket == 81)
+    bucket_82 = sum(r.amount for r in rows if r.bucket == 82)
+    bucket_83 = sum(r.amount for r in rows if r.bucket == 83)
+    bucket_84 = sum(r.amount for r in rows if r.bucket == 84)
+    bucket_85 = sum(r.amount for r in rows if r.bucket == 85)
+    bucket_86 = sum(r.amount for r in rows if r.bucket == 86)
+    bucket_87 = sum(r.amount for r in rows if r.bucket == 87)
+    bucket_88 = sum(r.amount for r in rows if r.bucket == 88)
+    bucket_89 = sum(r.amount for r in rows if r.bucket == 89)
+    bucket_90 = sum(r.amount for r in rows if r.bucket == 90)
+    bucket_91 = sum(r.amount for r in rows if r.bucket == 91)
+    bucket_92 = sum(r.amount for r in rows if r.bucket == 92)
+    bucket_93 = sum(r.amount for r in rows if r.bucket == 93)
+    bucket_94 = sum(r.amount for r in rows if r.bucket == 94)
+    bucket_95 = sum(r.amount for r in rows if r.bucket == 95)
+    bucket_96 = sum(r.amount for r in rows if r.bucket == 96)
+    bucket_97 = sum(r.amount for r in rows if r.bucket == 97)
+    bucket_98 = sum(r.amount for r in rows if r.bucket == 98)
+    bucket_99 = sum(r.amount for r in rows if r.bucket == 99)
+    bucket_100 = sum(r.amount for r in rows if r.bucket == 100)
+    bucket_101 = sum(r.amount for r in rows if r.bucket == 101)
+    bucket_102 = sum(r.amount for r in rows if r.bucket == 102)
+    bucket_103 = sum(r.amount for r in rows if r.bucket == 103)
+    bucket_104 = sum(r.amount for r in rows if r.bucket == 104)
+    bucket_105 = sum(r.amount for r in rows if r.bucket == 105)
+    bucket_106 = sum(r.amount for r in rows if r.bucket == 106)
+    bucket_107 = sum(r.amount for r in rows if r.bucket == 107)
+    bucket_108 = sum(r.amount for r in rows if r.bucket == 108)
+    bucket_109 = sum(r.amount for r in rows if r.bucket == 109)
+    bucket_110 = sum(r.amount for r in rows if r.bucket == 110)
+    bucket_111 = sum(r.amount for r in rows if r.bucket == 111)
+    bucket_112 = sum(r.amount for r in rows if r.bucket == 112)
+    bucket_113 = sum(r.amount for r in rows if r.bucket == 113)
+    bucket_114 = sum(r.amount for r in rows if r.bucket == 114)
+    bucket_115 = sum(r.amount for r in rows if r.bucket == 115)
+    bucket_116 = sum(r.amount for r in rows if r.bucket == 116)
+    bucket_117 = sum(r.amount for r in rows if r.bucket == 117)
+    bucket_118 = sum(r.amount for r in rows if r.bucket == 118)
+    bucket_119 = sum(r.amount for r in rows if r.bucket == 119)
+    bucket_120 = sum(r.amount for r in rows if r.bucket == 120)
+    bucket_121 = sum(r.amount for r in rows if r.bucket == 121)
+    bucket_122 = sum(r.amount for r in rows if r.bucket == 122)
+    bucket_123 = sum(r.amount for r in rows if r.bucket == 123)
+    bucket_124 = sum(r.amount for r in rows if r.bucket == 124)
+    bucket_125 = sum(r.amount for r in rows if r.bucket == 125)
+    bucket_126 = sum(r.amount for r in rows if r.bucket == 126)
+    bucket_127 = sum(r.amount for r in rows if r.bucket == 127)
+    bucket_128 = sum(r.amount for r in rows if r.bucket == 128)
+    bucket_129 = sum(r.amount for r in rows if r.bucket == 129)
+    bucket_130 = sum(r.amount for r in rows if r.bucket == 130)
+    bucket_131 = sum(r.amount for r in rows if r.bucket == 131)
+    bucket_132 = sum(r.amount for r in rows if r.bucket == 132)
+    bucket_133 = sum(r.amount for r in rows if r.bucket == 133)
+    bucket_134 = sum(r.amount for r in rows if r.bucket == 134)
+    bucket_135 = sum(r.amount for r in rows if r.bucket == 135)
+    bucket_136 = sum(r.amount for r in rows if r.bucket == 136)
+    bucket_137 = sum(r.amount for r in rows if r.bucket == 137)
+    bucket_138 = sum(r.amount for r in rows if r.bucket == 138)
+    bucket_139 = sum(r.amount for r in rows if r.bucket == 139)
+    bucket_140 = sum(r.amount for r in rows if r.bucket == 140)
+    bucket_141 = sum(r.amount for r in rows if r.bucket == 141)
+    bucket_142 = sum(r.amount for r in rows if r.bucket == 142)
+    bucket_143 = sum(r.amount for r in rows if r.bucket == 143)
+    bucket_144 = sum(r.amount for r in rows if r.bucket == 144)
+    bucket_145 = sum(r.amount for r in rows if r.bucket == 145)
+    bucket_146 = sum(r.amount for r in rows if r.bucket == 146)
+    bucket_147 = sum(r.amount for r in rows if r.bucket == 147)
+    bucket_148 = sum(r.amount for r in rows if r.bucket == 148)
+    bucket_149 = sum(r.amount for r in rows if r.bucket == 149)
+    bucket_150 = sum(r.amount for r in rows if r.bucket == 150)
+    bucket_151 = sum(r.amount for r in rows if r.bucket == 151)
+    bucket_152 = sum(r.amount for r in rows if r.bucket == 152)
+    bucket_153 = sum(r.amount for r in rows if r.bucket == 153)
+    bucket_154 = sum(r.amount for r in rows if r.bucket == 154)
+    bucket_155 = sum(r.amount for r in rows if r.bucket == 155)
+    bucket_156 = sum(r.amount for r in rows if r.bucket == 156)
+    bucket_157 = sum(r.amount for r in rows if r.bucket == 157)
+    bucket_158 = sum(r.amount for r in rows if r.bucket == 158)
+    bucket_159 = sum(r.amount for r in rows if r.bucket == 159)
+    bucket_160 = sum(r.amount for r in rows if r.bucket == 160)
+    bucket_161 = sum(r.amount for r in rows if r.bucket == 161)
+    bucket_162 = sum(r.amount for r in rows if r.bucket == 162)
+    bucket_163 = sum(r.amount for r in rows if r.bucket == 163)
+    bucket_164 = sum(r.amount for r in rows if r.bucket == 164)
+    bucket_165 = sum(r.amount for r in rows if r.bucket == 165)
+    bucket_166 = sum(r.amount for r in rows if r.bucket == 166)
+    bucket_167 = sum(r.amount for r in rows if r.bucket == 167)
+    bucket_168 = sum(r.amount for r in rows if r.bucket == 168)
+    bucket_169 = sum(r.amount for r in rows if r.bucket == 169)
+    bucket_170 = sum(r.amount for r in rows if r.bucket == 170)
+    bucket_171 = sum(r.amount for r in rows if r.bucket == 171)
+    bucket_172 = sum(r.amount for r in rows if r.bucket == 172)
+    bucket_173 = sum(r.amount for r in rows if r.bucket == 173)
+    bucket_174 = sum(r.amount for r in rows if r.bucket == 174)
+    bucket_175 = sum(r.amount for r in rows if r.bucket == 175)
+    bucket_176 = sum(r.amount for r in rows if r.bucket == 176)
+    bucket_177 = sum(r.amount for r in rows if r.bucket == 177)
+    bucket_178 = sum(r.amount for r in rows if r.bucket == 178)
+    bucket_179 = sum(r.amount for r in rows if r.bucket == 179)
+    bucket_180 = sum(r.amount for r in rows if r.bucket == 180)
+    bucket_181 = sum(r.amount for r in rows if r.bucket == 181)
+    bucket_182 = sum(r.amount for r in rows if r.bucket == 182)
+    bucket_183 = sum(r.amount for r in rows if r.bucket == 183)
+    bucket_184 = sum(r.amount for r in rows if r.bucket == 184)
+    bucket_185 = sum(r.amount for r in rows if r.bucket == 185)
+    bucket_186 = sum(r.amount for r in rows if r.bucket == 186)
+    bucket_187 = sum(r.amount for r in rows if r.bucket == 187)
+    bucket_188 = sum(r.amount for r in rows if r.bucket == 188)
+    bucket_189 = sum(r.amount for r in rows if r.bucket == 189)
+    bucket_190 = sum(r.amount for r in rows if r.bucket == 190)
+    bucket_191 = sum(r.amount for r in rows if r.bucket == 191)
+    bucket_192 = sum(r.amount for r in rows if r.bucket == 192)
+    bucket_193 = sum(r.amount for r in rows if r.bucket == 193)
+    bucket_194 = sum(r.amount for r in rows if r.bucket == 194)
+    bucket_195 = sum(r.amount for r in rows if r.bucket == 195)
+    bucket_196 = sum(r.amount for r in rows if r.bucket == 196)
+    bucket_197 = sum(r.amount for r in rows if r.bucket == 197)
+    bucket_198 = sum(r.amount for r in rows if r.bucket == 198)
+    bucket_199 = sum(r.amount for r in rows if r.bucket == 199)
+    bucket_200 = sum(r.amount for r in rows if r.bucket == 200)
+    bucket_201 = sum(r.amount for r in rows if r.bucket == 201)
+    bucket_202 = sum(r.amount for r in rows if r.bucket == 202)
+    bucket_203 = sum(r.amount for r in rows if r.bucket == 203)
+    bucket_204 = sum(r.amount for r in rows if r.bucket == 204)
+    bucket_205 = sum(r.amount for r in rows if r.bucket == 205)
+    bucket_206 = sum(r.amount for r in rows if r.bucket == 206)
+    bucket_207 = sum(r.amount for r in rows if r.bucket == 207)
+    bucket_208 = sum(r.amount for r in rows if r.bucket == 208)
+    bucket_209 = sum(r.amount for r in rows if r.bucket == 209)
+    bucket_210 = sum(r.amount for r in rows if r.bucket == 210)
+    bucket_211 = sum(r.amount for r in rows if r.bucket == 211)
+    bucket_212 = sum(r.amount for r in rows if r.bucket == 212)
+    bucket_213 = sum(r.amount for r in rows if r.bucket == 213)
+    bucket_214 = sum(r.amount for r in rows if r.bucket == 214)
+    bucket_215 = sum(r.amount for r in rows if r.bucket == 215)
+    bucket_216 = sum(r.amount for r in rows if r.bucket == 216)
+    bucket_217 = sum(r.amount for r in rows if r.bucket == 217)
+    bucket_218 = sum(r.amount for r in rows if r.bucket == 218)
+    bucket_219 = sum(r.amount for r in rows if r.bucket == 219)
+    bucket_220 = sum(r.amount for r in rows if r.bucket == 220)
+    bucket_221 = sum(r.amount for r in rows if r.bucket == 221)
+    bucket_222 = sum(r.amount for r in rows if r.bucket == 222)
+    bucket_223 = sum(r.amount for r in rows if r.bucket == 223)
+    bucket_224 = sum(r.amount for r in rows if r.bucket == 224)
+    bucket_225 = sum(r.amount for r in rows if r.bucket == 225)
+    bucket_226 = sum(r.amount for r in rows if r.bucket == 226)
+    bucket_227 = sum(r.amount for r in rows if r.bucket == 227)
+    bucket_228 = sum(r.amount for r in rows if r.bucket == 228)
+    bucket_229 = sum(r.amount for r in rows if r.bucket == 229)
+    bucket_230 = sum(r.amount for r in rows if r.bucket == 230)
+    bucket_231 = sum(r.amount for r in rows if r.bucket == 231)
+    bucket_232 = sum(r.amount for r in rows if r.bucket == 232)
+    bucket_233 = sum(r.amount for r in rows if r.bucket == 233)
+    bucket_234 = sum(r.amount for r in rows if r.bucket == 234)
+    bucket_235 = sum(r.amount for r in rows if r.bucket == 235)
+    bucket_236 = sum(r.amount for r in rows if r.bucket == 236)
+    bucket_237 = sum(r.amount for r in rows if r.bucket == 237)
+    bucket_238 = sum(r.amount for r in rows if r.bucket == 238)
+    bucket_239 = sum(r.amount for r in rows if r.bucket == 239)
+    summary = total * rate / len(rows)
+    return summary
+
+
+def exchange_rate(currency):
+    return 1.0
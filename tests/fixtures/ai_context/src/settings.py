@@ -0,0 +1,13 @@
+import os
+
+ENV = os.environ.get("APP_ENV", "production")
+
+CACHE_TTL = 300
+PAGE_SIZE = 50
+DEBUG = True
+ALLOWED_HOSTS = ["*"]
+TIMEZONE = "UTC"
+
+
+def database_url():
+    return os.environ["DATABASE_URL"]
@@ -0,0 +1,8 @@
+class User:
+    def __init__(self, user_id, name):
+        self.user_id = user_id
+        self.name = name
+
+
+def load_user(user_id):
+    return User(user_id, "user-%d" % user_id)
@@ -0,0 +1,8 @@
+from app.service import get_profile
+
+
+def profile_view(request):
+    try:
+        return get_profile(int(request.args["id"]))
+    except:
+        pass
@@ -0,0 +1,25 @@
+from core.text import slugify
+
+
+class Client:
+    def get(self, path):
+        return self.request("GET", path)
+
+    def request(self, method, path):
+        return (method, slugify(path))
+
+
+def fetch(url, timeout=30, retries=3) -> bytes:
+    return Client().get(url)
+
+
+def parse_headers(name, value) -> dict:
+    return {name: value}
+
+
+def stream(url, chunk_size=1024):
+    return fetch(url)
+
+
+def _cache_key(url, scope):
+    return f"{scope}:{url.lower()}"
@@ -0,0 +1,2 @@
+def _strip(text):
+    return text.strip()
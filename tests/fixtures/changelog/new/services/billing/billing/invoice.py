@@ -0,0 +1,41 @@
+class Invoice:
+    def __init__(self, items, currency="EUR"):
+        self.items = items
+        self.currency = currency
+
+    def subtotal(self):
+        return sum(item.price for item in self.items)
+
+    def tax(self):
+        return self.subtotal() * 0.2
+
+    def shipping(self):
+        return 0 if self.subtotal() > 50 else 5
+
+    def discount(self):
+        return 0
+
+    def total(self):
+        return self.subtotal() + self.tax() + self.shipping() - self.discount()
+
+    def lines(self):
+        return [item.name for item in self.items]
+
+    def is_empty(self):
+        return not self.items
+
+    def currency_symbol(self):
+        return {"EUR": "€", "USD": "$"}.get(self.currency, "")
+
+    def formatted(self):
+        return f"{self.currency_symbol()}{self.total():.2f}"
+
+    def to_dict(self):
+        return {"lines": self.lines(), "total": self.total()}
+
+    def copy(self):
+        return Invoice(list(self.items), self.currency)
+
+
+def render(invoice):
+    return f"Total: {invoice.formatted()}"
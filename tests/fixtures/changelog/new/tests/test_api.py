@@ -0,0 +1,9 @@
+from core.api import fetch
+
+
+def test_fetch():
+    assert fetch("a", retries=1)
+
+
+def test_fetch_default():
+    assert fetch("b")
@@ -0,0 +1,25 @@
+from core.util import slugify
+
+
+class Client:
+    def get(self, path):
+        return self.request("GET", path)
+
+    def request(self, method, path):
+        return (method, slugify(path))
+
+
+def fetch(url, timeout=30) -> bytes:
+    return Client().get(url)
+
+
+def legacy_fetch(url):
+    return fetch(url)
+
+
+def parse_header(name, value) -> dict:
+    return {name: value}
+
+
+def _cache_key(url):
+    return url.lower()
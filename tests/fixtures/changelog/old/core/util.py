@@ -0,0 +1,6 @@
+def slugify(text: str) -> str:
+    return text.lower().replace(" ", "-")
+
+
+def _strip(text):
+    return text.strip()
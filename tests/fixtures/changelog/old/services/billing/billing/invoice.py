@@ -0,0 +1,10 @@
+class Invoice:
+    def __init__(self, items):
+        self.items = items
+
+    def total(self):
+        return sum(item.price for item in self.items)
+
+
+def render(invoice):
+    return f"Total: {invoice.total()}"
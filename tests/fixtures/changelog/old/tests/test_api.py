@@ -0,0 +1,5 @@
+from core.api import fetch
+
+
+def test_fetch():
+    assert fetch("a")
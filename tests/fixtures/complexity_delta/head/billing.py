@@ -0,0 +1,56 @@
+def apply_discounts(order, customer):
+    total = order.total
+    if customer.vip:
+        total *= 0.85
+    if order.coupon:
+        total -= order.coupon.amount
+    if total < 0:
+        total = 0
+    for item in order.items:
+        if item.clearance:
+            total -= item.discount
+    return total
+
+
+def shipping_cost(order):
+    cost = 5
+    if order.express:
+        cost = 15
+    if order.weight > 10:
+        cost += 10
+    for item in order.items:
+        if item.fragile:
+            cost += 2
+        if item.oversized:
+            cost += 30
+    if order.international:
+        cost += 20
+    return cost
+
+
+def refund_total(order):
+    refund = 0
+    for item in order.items:
+        if item.returned:
+            refund += item.price
+        if item.damaged:
+            refund += item.shipping
+    if order.expedited:
+        refund += 10
+    if refund > order.total:
+        refund = order.total
+    return refund
+
+
+def loyalty_points(order, customer):
+    points = 0
+    if customer.member:
+        points += order.total // 10
+    if customer.birthday_month:
+        points *= 2
+    for item in order.items:
+        if item.bonus:
+            points += 5
+    if points > 1000:
+        points = 1000
+    return points
@@ -0,0 +1,25 @@
+import threading
+
+from fastapi import FastAPI
+
+app = FastAPI()
+ITEMS = []
+_lock = threading.Lock()
+_seen = set()
+
+
+@app.post("/items")
+def create_item(item: dict):
+    ITEMS.append(item)
+    return item
+
+
+@app.get("/items")
+def list_items():
+    return list(ITEMS)
+
+
+@app.put("/seen")
+def mark_seen(key: str):
+    with _lock:
+        _seen.add(key)
@@ -0,0 +1,69 @@
+import threading
+from concurrent.futures import ThreadPoolExecutor
+
+CACHE = {}
+TOTALS = []
+LOCK = threading.Lock()
+processed = 0
+
+
+def record(key, value):
+    CACHE[key] = value
+
+
+def record_locked(value):
+    with LOCK:
+        TOTALS.append(value)
+
+
+def count():
+    global processed
+    processed += 1
+
+
+def lookup(key):
+    return CACHE.get(key)
+
+
+def worker(items):
+    for key, value in items:
+        record(key, value)
+        record_locked(value)
+        count()
+        lookup(key)
+
+
+class Registry:
+    items = []
+
+    def __init__(self):
+        self.lock = threading.Lock()
+
+    def register(self, item):
+        self.items.append(item)
+
+    def register_safe(self, item):
+        self.lock.acquire()
+        try:
+            self.items.append(item)
+        finally:
+            self.lock.release()
+
+
+def local_only(items):
+    CACHE = {}
+    CACHE.update(items)
+    return CACHE
+
+
+def run(items):
+    registry = Registry()
+    threading.Thread(target=worker, args=(items,)).start()
+    threading.Thread(target=local_only, args=(items,)).start()
+    with ThreadPoolExecutor() as executor:
+        executor.submit(registry.register, items[0])
+        executor.submit(registry.register_safe, items[0])
+
+
+def sequential(items):
+    CACHE.clear()
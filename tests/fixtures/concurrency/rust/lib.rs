@@ -0,0 +1,24 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static mut COUNTER: u32 = 0;
+static LIMIT: u32 = 3;
+static REQUESTS: AtomicU32 = AtomicU32::new(0);
+
+pub struct Handle(*mut u8);
+
+unsafe impl Send for Handle {}
+unsafe impl Sync for Handle {}
+
+impl Clone for Handle {
+    fn clone(&self) -> Self {
+        Handle(self.0)
+    }
+}
+
+pub fn bump() -> u32 {
+    REQUESTS.fetch_add(1, Ordering::SeqCst);
+    unsafe {
+        COUNTER += 1;
+        COUNTER.min(LIMIT)
+    }
+}
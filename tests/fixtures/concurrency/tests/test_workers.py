@@ -0,0 +1,14 @@
+import threading
+
+RESULTS = []
+
+
+def collect(value):
+    RESULTS.append(value)
+
+
+def test_collect():
+    thread = threading.Thread(target=collect, args=(1,))
+    thread.start()
+    thread.join()
+    assert RESULTS == [1]
@@ -0,0 +1,11 @@
+const items: string[] = [];
+
+export async function POST(request: Request) {
+  const { name } = await request.json();
+  items.push(name);
+  return Response.json({ count: items.length });
+}
+
+export async function GET() {
+  return Response.json(items);
+}
@@ -0,0 +1,39 @@
+import express from "express";
+import { Mutex } from "async-mutex";
+
+const app = express();
+const sessions = new Map<string, string>();
+const mutex = new Mutex();
+let hits = 0;
+const config = { region: "eu" };
+
+app.post("/login", (req, res) => {
+  sessions.set(req.body.user, req.body.token);
+  res.sendStatus(204);
+});
+
+app.get("/count", countHits);
+
+function countHits(req, res) {
+  hits++;
+  res.json({ hits });
+}
+
+app.post("/logout", async (req, res) => {
+  await mutex.runExclusive(() => {
+    sessions.delete(req.body.user);
+  });
+  res.sendStatus(204);
+});
+
+app.get("/region", (req, res) => {
+  res.json({ region: config.region });
+});
+
+app.get("/local", (req, res) => {
+  const sessions = new Map<string, string>();
+  sessions.set("a", "b");
+  res.json(Object.fromEntries(sessions));
+});
+
+export default app;
@@ -0,0 +1,13 @@
+from fastapi import FastAPI
+
+from app.routers import orders, users
+
+app = FastAPI(title="Shop")
+
+app.include_router(users.router)
+app.include_router(orders.router, prefix="/orders")
+
+
+@app.get("/health")
+def health():
+    return {"status": "ok"}
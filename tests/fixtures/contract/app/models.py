@@ -0,0 +1,29 @@
+from pydantic import BaseModel, ConfigDict, Field
+from pydantic.alias_generators import to_camel
+
+
+class UserBase(BaseModel):
+    email: str
+    display_name: str | None = None
+
+
+class UserCreate(UserBase):
+    password: str = Field(..., min_length=12)
+
+
+class UserUpdate(BaseModel):
+    display_name: str | None = None
+    marketing_opt_in: bool = False
+
+
+class OrderItem(BaseModel):
+    sku: str
+    quantity: int = 1
+
+
+class OrderCreate(BaseModel):
+    model_config = ConfigDict(alias_generator=to_camel)
+
+    user_id: int
+    items: list[OrderItem]
+    coupon_code: str | None = None
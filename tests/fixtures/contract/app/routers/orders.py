@@ -0,0 +1,26 @@
+from fastapi import APIRouter
+
+from app.models import OrderCreate
+from app.store import store
+
+router = APIRouter(tags=["orders"])
+
+
+@router.get("/")
+def list_orders(user_id: int | None = None):
+    return store.orders_of(user_id)
+
+
+@router.post("/", status_code=201)
+def create_order(order: OrderCreate):
+    return store.add_order(order)
+
+
+@router.get("/{order_id}")
+def get_order(order_id: int):
+    return store.orders[order_id]
+
+
+@router.delete("/{order_id}", status_code=204)
+def cancel_order(order_id: int):
+    store.cancel_order(order_id)
@@ -0,0 +1,29 @@
+from fastapi import APIRouter, HTTPException
+
+from app.models import UserCreate, UserUpdate
+from app.store import store
+
+router = APIRouter(prefix="/users", tags=["users"])
+
+
+@router.get("")
+def list_users(limit: int = 50):
+    return store.users[:limit]
+
+
+@router.get("/{user_id}")
+def get_user(user_id: int):
+    user = store.users.get(user_id)
+    if user is None:
+        raise HTTPException(status_code=404)
+    return user
+
+
+@router.post("", status_code=201)
+def create_user(user: UserCreate):
+    return store.add_user(user)
+
+
+@router.patch("/{user_id}")
+def update_user(user_id: int, changes: UserUpdate):
+    return store.update_user(user_id, changes)
@@ -0,0 +1,4 @@
+export async function ping(): Promise<boolean> {
+  const res = await fetch("/api/health");
+  return res.ok;
+}
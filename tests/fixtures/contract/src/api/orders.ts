@@ -0,0 +1,21 @@
+import axios from "axios";
+
+const api = axios.create({ baseURL: "/api" });
+
+export interface OrderItem {
+  sku: string;
+  quantity: number;
+}
+
+export interface NewOrder {
+  userId: number;
+  items: OrderItem[];
+  couponCode?: string;
+}
+
+export const listOrders = (userId: number) =>
+  api.get("/orders/", { params: { user_id: userId } });
+
+export const getOrder = (id: number) => api.get(`/orders/${id}`);
+
+export const placeOrder = (order: NewOrder) => api.post("/orders/", order);
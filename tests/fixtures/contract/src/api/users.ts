@@ -0,0 +1,38 @@
+export interface NewUser {
+  emailAddress: string;
+  display_name?: string;
+  password: string;
+}
+
+export async function listUsers() {
+  const res = await fetch("/api/users");
+  return res.json();
+}
+
+export async function getUser(id: number) {
+  const res = await fetch(`/api/users/${id}`);
+  return res.json();
+}
+
+export async function createUser(user: NewUser) {
+  const res = await fetch("/api/users", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify(user),
+  });
+  return res.json();
+}
+
+export async function renameUser(id: number, displayName: string) {
+  await fetch(`/api/users/${id}`, {
+    method: "PATCH",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ display_name: displayName }),
+  });
+}
+
+export async function uploadAvatar(id: number, file: Blob) {
+  const form = new FormData();
+  form.append("file", file);
+  await fetch(`/api/users/${id}/avatar`, { method: "PUT", body: form });
+}
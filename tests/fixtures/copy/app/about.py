@@ -0,0 +1,12 @@
+from app.brand import OldBrand
+
+NOTICE = "Copyright (c) 2018-2021 Acme Inc."
+
+
+def about(settings: OldBrand) -> str:
+    # OldBrand was renamed in 2024; the settings class keeps its name
+    return f"Powered by LegacyName {settings.OldBrand}"
+
+
+def title(brand: OldBrand) -> str:
+    return f"{OldBrand.label} — {brand.name}"
@@ -0,0 +1,16 @@
+import { OldBrand } from "./brand";
+
+const year = new Date().getFullYear();
+
+export function Footer() {
+  const client = new OldBrand.Client();
+  const banner = "New in version 3.2: dark mode";
+  const recent = "Improved in v4.2";
+  return (
+    <footer title={`© ${year} NewBrand`}>
+      © 2019 Acme Inc. All rights reserved.
+      <span>Welcome to OldBrand</span>
+      {client.ready && "OldBrand is ready. Thanks for using OldBrand!"}
+    </footer>
+  );
+}
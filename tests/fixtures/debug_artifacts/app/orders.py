@@ -0,0 +1,37 @@
+import logging
+
+logger = logging.getLogger(__name__)
+
+
+def total(order):
+    breakpoint()
+    print("here")
+    subtotal = 0
+    for line in order.lines:
+        subtotal += line.price
+    print(subtotal)
+    logger.info("Computed total %s for order %s", subtotal, order.id)
+    print(f"Order {order.id} total: {subtotal}")
+    return subtotal
+
+
+def refund(order):
+    import pdb; pdb.set_trace()
+    # Refunds used to be issued line by line:
+    # for line in order.lines:
+    #     if line.refundable:
+    #         provider.refund(line.id, amount=line.price)
+    #         line.state = "refunded"
+    # order.save()
+    return provider.refund_all(order.id)
+
+
+def summary(orders):
+    # Summarise orders by state for the operator running the nightly job.
+    # Each state maps to the number of orders in it (cancelled orders are
+    # counted too), and the result is printed as a table; nothing here is
+    # persisted, so running it twice is harmless. See the runbook for the
+    # expected output when the payment provider is down.
+    print("Order summary")
+    print("-" * 40)
+    return {o.state: o for o in orders}
@@ -0,0 +1,17 @@
+package main
+
+import (
+	"fmt"
+	"log"
+	"runtime"
+)
+
+func main() {
+	addr := ":8080"
+	runtime.Breakpoint()
+	fmt.Println("HERE!!!")
+	fmt.Printf("%v\n", addr)
+	fmt.Printf("listening on %s\n", addr)
+	log.Printf("starting server on %s", addr)
+	serve(addr)
+}
@@ -0,0 +1,18 @@
+require "logger"
+
+class Report
+  def initialize(rows)
+    @rows = rows
+    @log = Logger.new($stdout)
+  end
+
+  def render
+    binding.pry
+    puts "asdf"
+    p @rows
+    @log.info("Rendering #{@rows.size} rows")
+    puts "Report (#{@rows.size} rows)"
+    byebug
+    @rows.map(&:to_s).join("\n")
+  end
+end
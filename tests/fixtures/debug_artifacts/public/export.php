@@ -0,0 +1,12 @@
+<?php
+
+function export_users(array $users): string
+{
+    dd($users);
+    var_dump($users);
+    print_r("here");
+    error_log("Exporting " . count($users) . " users");
+    $csv = implode("\n", array_map('format_row', $users));
+    printf("Exported %d users\n", count($users));
+    return $csv;
+}
@@ -0,0 +1,22 @@
+use log::info;
+
+/// Starts the server.
+///
+/// ```
+/// let config = load_config();
+/// run(config, 0);
+/// ```
+fn main() {
+    let config = load_config();
+    dbg!(&config);
+    println!("{:?}", config);
+    println!("Listening on {}", config.addr);
+    info!("started with {} workers", config.workers);
+    let total = dbg!(compute(&config));
+    // let cache = Cache::new(config.cache_size);
+    // for key in config.warm_keys.iter() {
+    //     cache.insert(key.clone(), load(key));
+    // }
+    // cache.flush();
+    run(config, total);
+}
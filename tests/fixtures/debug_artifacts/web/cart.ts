@@ -0,0 +1,16 @@
+import { logger } from "./logger";
+import { fetchPrice } from "./pricing";
+
+export async function addItem(cart: Cart, item: Item): Promise<Cart> {
+  debugger;
+  console.log("got here 2");
+  console.log(`${item}`);
+  console.log("Adding item", item.sku);
+  logger.debug("cart updated", { size: cart.items.length });
+  try {
+    item.price = await fetchPrice(item.sku);
+  } catch (err) {
+    console.error("Failed to reach pricing service", err);
+  }
+  return { ...cart, items: [...cart.items, item] };
+}
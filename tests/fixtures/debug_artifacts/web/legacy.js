@@ -0,0 +1,16 @@
+/**
+ * Cart totals.
+ *
+ * Prices are in cents; quantities are whole numbers. The reducer below
+ * replaced the loop that used to live here.
+ */
+// function legacyTotal(cart) {
+//   let sum = 0;
+//   for (const item of cart.items) {
+//     sum += item.price * item.qty;
+//   }
+//   return sum;
+// }
+export function total(cart) {
+  return cart.items.reduce((sum, i) => sum + i.price * i.qty, 0);
+}
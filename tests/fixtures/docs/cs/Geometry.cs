@@ -0,0 +1,22 @@
+namespace Geometry
+{
+    /// <summary>
+    /// Geometry helpers.
+    /// </summary>
+    public static class Shapes
+    {
+        /// <summary>Area of a rectangle.</summary>
+        [Pure]
+        public static double Area(double w, double h) => w * h;
+
+        public static double Perimeter(double w, double h) => 2 * (w + h);
+
+        private static double Helper() => 1;
+    }
+
+    /// <summary>Something with an area.</summary>
+    public interface IShape
+    {
+        double Area();
+    }
+}
@@ -0,0 +1,21 @@
+package geometry
+
+// Area returns the area of a rectangle.
+func Area(w, h float64) float64 { return w * h }
+
+func Perimeter(w, h float64) float64 { return 2 * (w + h) }
+
+func helper() float64 { return 1 }
+
+// Rect is a rectangle.
+type Rect struct {
+	W, H float64
+}
+
+// Scale returns the rectangle scaled by f.
+func (r *Rect) Scale(f float64) Rect { return Rect{r.W * f, r.H * f} }
+
+func (r *Rect) GetWidth() float64 { return r.W }
+
+//go:noinline
+func (r *Rect) Diagonal() float64 { return 0 }
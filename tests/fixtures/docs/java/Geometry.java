@@ -0,0 +1,35 @@
+package geometry;
+
+/**
+ * Geometry helpers.
+ */
+public class Geometry {
+    /** Area of a rectangle. */
+    public static double area(double w, double h) {
+        return w * h;
+    }
+
+    /**
+     * Perimeter of a rectangle.
+     */
+    @Deprecated
+    public static double perimeter(double w, double h) {
+        return 2 * (w + h);
+    }
+
+    public static double diagonal(double w, double h) {
+        return Math.sqrt(w * w + h * h);
+    }
+
+    private static double helper() {
+        return 1;
+    }
+
+    public double getScale() {
+        return 1;
+    }
+}
+
+interface Shape {
+    double area();
+}
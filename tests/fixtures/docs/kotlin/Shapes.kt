@@ -0,0 +1,3 @@
+package geometry
+
+fun area(w: Double, h: Double): Double = w * h
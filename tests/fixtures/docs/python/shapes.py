@@ -0,0 +1,40 @@
+"""Circle helpers."""
+
+__all__ = ["area", "perimeter", "Circle"]
+
+MAX_SIDES = 12
+
+
+def area(radius):
+    """Area of a circle of `radius`."""
+    return 3.14159 * radius * radius
+
+
+def perimeter(radius):
+    return 2 * 3.14159 * radius
+
+
+def helper():
+    return 1
+
+
+def _private():
+    return 2
+
+
+class Circle:
+    """A circle."""
+
+    def __init__(self, radius):
+        self.radius = radius
+
+    @property
+    def diameter(self):
+        return self.radius * 2
+
+    def scale(self, factor):
+        """Scale by `factor`."""
+        return Circle(self.radius * factor)
+
+    def describe(self):
+        return "circle"
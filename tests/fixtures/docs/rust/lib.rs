@@ -0,0 +1,45 @@
+//! Rectangle helpers
+
+/// Area of a rectangle.
+///
+/// Width times height.
+pub fn area(width: f64, height: f64) -> f64 {
+    width * height
+}
+
+pub fn perimeter(width: f64, height: f64) -> f64 {
+    2.0 * (width + height)
+}
+
+fn helper() -> f64 {
+    1.0
+}
+
+pub const MAX_SIDES: usize = 12;
+
+/** A rectangle. */
+#[derive(Debug)]
+pub struct Rect {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    /// Square of side `side`.
+    #[must_use]
+    pub fn square(side: f64) -> Self {
+        Rect { width: side, height: side }
+    }
+
+    pub fn width(&self) -> f64 { self.width }
+
+    pub fn is_square(&self) -> bool {
+        self.width == self.height
+    }
+}
+
+/// Something with an area.
+pub trait Shape {
+    /// Area of the shape.
+    fn area(&self) -> f64;
+}
@@ -0,0 +1,5 @@
+from python.shapes import area
+
+
+def test_area():
+    assert area(1) > 3
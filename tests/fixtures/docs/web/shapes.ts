@@ -0,0 +1,31 @@
+/**
+ * Area of a rectangle.
+ */
+export function area(w: number, h: number): number {
+  return w * h;
+}
+
+export function perimeter(w: number, h: number): number {
+  return 2 * (w + h);
+}
+
+function helper(): number {
+  return 1;
+}
+
+export const MAX_SIDES = 12;
+
+/** A rectangle. */
+export class Rect {
+  w = 0;
+  h = 0;
+
+  /** Scaled copy. */
+  scale(f: number): Rect {
+    return new Rect();
+  }
+
+  private check(): boolean {
+    return helper() > 0;
+  }
+}
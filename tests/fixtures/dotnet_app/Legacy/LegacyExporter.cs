@@ -0,0 +1,9 @@
+namespace Shop.Legacy;
+
+public class LegacyExporter
+{
+    public void Export(string path)
+    {
+        File.WriteAllText(path, "");
+    }
+}
@@ -0,0 +1,17 @@
+using System.Text.RegularExpressions;
+
+namespace Shop.Orders;
+
+public partial class Order
+{
+    private void Validate()
+    {
+        if (!IdPattern().IsMatch(Normalize(Id)))
+        {
+            throw new InvalidOperationException($"bad order id {Id}");
+        }
+    }
+
+    [GeneratedRegex(@"^[A-Z]-\d+$")]
+    private static partial Regex IdPattern();
+}
@@ -0,0 +1,27 @@
+namespace Shop.Orders;
+
+public partial class Order
+{
+    public string Id { get; }
+
+    private Order(string id)
+    {
+        Id = id;
+    }
+
+    public static Order Create(string id)
+    {
+        return new Order(Normalize(id));
+    }
+
+    public void Submit()
+    {
+        Validate();
+        Console.WriteLine($"submitted {Id}");
+    }
+
+    private static string Normalize(string id)
+    {
+        return id.Trim().ToUpperInvariant();
+    }
+}
@@ -0,0 +1,9 @@
+namespace Shop.Orders;
+
+public class OrderService
+{
+    public void Place(Order order)
+    {
+        order.Submit();
+    }
+}
@@ -0,0 +1,15 @@
+using Shop.Orders;
+using Shop.Validation;
+using Shop.ViewModels;
+
+var service = new OrderService();
+var order = Order.Create("A-1");
+service.Place(order);
+
+if (!EmailValidator.IsValid(args[0]))
+{
+    Console.WriteLine("invalid email");
+}
+
+var cart = new CartViewModel();
+cart.ClearCommand.Execute(null);
@@ -0,0 +1,11 @@
+using Grpc.Core;
+
+namespace Shop.Services;
+
+public class GreeterService : Greeter.GreeterBase
+{
+    public override Task<HelloReply> SayHello(HelloRequest request, ServerCallContext context)
+    {
+        return Task.FromResult(new HelloReply { Message = $"Hello {request.Name}" });
+    }
+}
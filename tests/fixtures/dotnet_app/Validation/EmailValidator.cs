@@ -0,0 +1,11 @@
+using System.Text.RegularExpressions;
+
+namespace Shop.Validation;
+
+public static partial class EmailValidator
+{
+    public static bool IsValid(string email) => EmailPattern().IsMatch(email);
+
+    [GeneratedRegex(@"^[^@\s]+@[^@\s]+\.[a-z]+$", RegexOptions.IgnoreCase)]
+    private static partial Regex EmailPattern();
+}
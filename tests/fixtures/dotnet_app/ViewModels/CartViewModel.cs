@@ -0,0 +1,16 @@
+using CommunityToolkit.Mvvm.ComponentModel;
+using CommunityToolkit.Mvvm.Input;
+
+namespace Shop.ViewModels;
+
+public partial class CartViewModel : ObservableObject
+{
+    [ObservableProperty]
+    private int _itemCount;
+
+    [RelayCommand]
+    private void Clear()
+    {
+        ItemCount = 0;
+    }
+}
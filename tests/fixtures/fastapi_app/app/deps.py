@@ -0,0 +1,24 @@
+from fastapi import Depends, Header, HTTPException
+
+from app.db import connect
+
+
+def get_db():
+    db = connect()
+    try:
+        yield db
+    finally:
+        db.close()
+
+
+def verify_token(x_token: str = Header(...)):
+    if x_token != "secret":
+        raise HTTPException(status_code=401)
+
+
+def get_current_user(db=Depends(get_db), token=Depends(verify_token)):
+    return {"id": 1}
+
+
+def legacy_lookup(user_id):
+    return None
@@ -0,0 +1,18 @@
+from fastapi import FastAPI, Request
+from fastapi.responses import JSONResponse
+
+from app.routers import orders, users
+
+app = FastAPI()
+app.include_router(users.router)
+app.include_router(orders.router)
+
+
+@app.on_event("startup")
+def warm_up():
+    pass
+
+
+@app.exception_handler(ValueError)
+async def value_error_handler(request: Request, exc: ValueError):
+    return JSONResponse(status_code=400, content={"detail": str(exc)})
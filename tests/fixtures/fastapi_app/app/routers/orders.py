@@ -0,0 +1,12 @@
+from fastapi import APIRouter, Depends
+
+from ..deps import get_current_user, get_db
+from ..tasks import send_receipt
+
+router = APIRouter(prefix="/orders")
+
+
+@router.post("/")
+def create_order(db=Depends(get_db), user=Depends(get_current_user)):
+    send_receipt.delay(user["id"])
+    return {"status": "created"}
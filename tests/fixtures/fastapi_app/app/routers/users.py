@@ -0,0 +1,16 @@
+from fastapi import APIRouter, Depends
+
+from app import deps
+from app.deps import get_db, verify_token
+
+router = APIRouter(prefix="/users")
+
+
+@router.get("/{user_id}")
+def read_user(user_id: int, db=Depends(get_db)):
+    return {"id": user_id}
+
+
+@router.get("/me", dependencies=[Depends(verify_token)])
+def read_me(user=Depends(deps.get_current_user)):
+    return user
@@ -0,0 +1,17 @@
+from celery import Celery, shared_task
+
+celery = Celery("orders")
+
+
+def render_receipt(order_id):
+    return f"Receipt for {order_id}"
+
+
+@celery.task(bind=True, max_retries=3)
+def send_receipt(self, order_id):
+    return render_receipt(order_id)
+
+
+@shared_task
+def purge_expired_orders():
+    pass
@@ -0,0 +1,9 @@
+import pytest
+from fastapi.testclient import TestClient
+
+from app.main import app
+
+
+@pytest.fixture
+def client(db):
+    return TestClient(app)
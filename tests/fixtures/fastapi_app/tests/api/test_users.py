@@ -0,0 +1,7 @@
+def test_read_user(client, user):
+    response = client.get(f"/users/{user['id']}")
+    assert response.status_code == 200
+
+
+def test_read_me(client, tmp_path):
+    assert client.get("/users/me").status_code == 401
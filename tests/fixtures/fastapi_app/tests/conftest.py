@@ -0,0 +1,15 @@
+import pytest
+
+from app.db import connect
+
+
+@pytest.fixture
+def db():
+    session = connect()
+    yield session
+    session.close()
+
+
+@pytest.fixture(name="user")
+def user_fixture(db):
+    return {"id": 1}
@@ -0,0 +1,24 @@
+"""Checkout entry points."""
+
+import ldclient
+
+ld = ldclient.get()
+
+
+def checkout(cart, user):
+    if ld.variation("checkout-v2", user, False):
+        return new_checkout(cart)
+    else:
+        return legacy_checkout(cart)
+
+
+def search(query, user):
+    if ld.variation("new-search", user, False):
+        return semantic_search(query)
+    return keyword_search(query)
+
+
+def export(report, user):
+    if ld.variation("beta-expotr", user, False):
+        return export_v2(report)
+    return export_v1(report)
@@ -0,0 +1,8 @@
+package services
+
+import ld "github.com/launchdarkly/go-server-sdk/v7"
+
+func UseNewInvoices(client *ld.LDClient, ctx Context) bool {
+	enabled, _ := client.BoolVariation("checkout-v2", ctx, false)
+	return enabled
+}
@@ -0,0 +1,13 @@
+package com.example.search;
+
+public class Search {
+    public String mode(LDClient client, LDContext ctx) {
+        if (FeatureGate.isOn("search-ranking")) {
+            return "ranked";
+        }
+        if (client.boolVariation("new-search", ctx, false)) {
+            return "semantic";
+        }
+        return "keyword";
+    }
+}
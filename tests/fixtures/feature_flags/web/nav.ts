@@ -0,0 +1,14 @@
+import { unleash } from "./flags";
+
+export function renderNav(region: string): string {
+  if (!unleash.isEnabled("dark-mode")) {
+    return "light";
+  }
+
+  if (unleash.isEnabled("old-nav")) {
+    return "legacy-nav";
+  }
+
+  const pricing = unleash.isEnabled(`pricing-${region}`);
+  return pricing ? "nav-with-pricing" : "nav";
+}
@@ -0,0 +1,62 @@
+"""Pricing rules: one branchy function well past the first-run threshold."""
+
+
+def price(order):
+    total = 0
+    for item in order.items:
+        if item.kind == "book":
+            total += item.price
+        elif item.kind == "food":
+            total += item.price * 1.05
+        elif item.kind == "wine":
+            total += item.price * 1.2
+        elif item.kind == "tobacco":
+            total += item.price * 1.5
+        elif item.kind == "toy":
+            total += item.price * 1.1
+        else:
+            total += item.price * 1.25
+        if item.discount and item.price > 100:
+            total -= 10
+        if item.discount and item.price > 50:
+            total -= 5
+        if item.gift or item.wrapped:
+            total += 3
+        if item.express or item.overnight:
+            total += 12
+        if item.fragile and item.price > 20:
+            total += 4
+    if order.member:
+        total *= 0.9
+    if order.coupon and order.coupon.valid:
+        total -= order.coupon.amount
+    if order.country == "DE" or order.country == "FR":
+        total += 7
+    if total < 0:
+        total = 0
+    return total
+
+
+def shipping(order):
+    cost = 5
+    if order.express:
+        cost += 10
+    if order.heavy:
+        cost += 8
+    if order.remote:
+        cost += 6
+    if order.member:
+        cost -= 3
+    if order.country == "US":
+        cost += 2
+    elif order.country == "CA":
+        cost += 4
+    elif order.country == "MX":
+        cost += 5
+    if order.gift:
+        cost += 1
+    if order.insured:
+        cost += 2
+    if cost < 0:
+        cost = 0
+    return cost
@@ -0,0 +1,29 @@
+"""Report queries built by string concatenation (INTENTIONAL SQL injection)."""
+
+
+def user_by_id(cursor, user_id):
+    cursor.execute("SELECT * FROM users WHERE id = " + user_id)
+
+
+def user_by_name(cursor, name):
+    cursor.execute("SELECT * FROM users WHERE name = '" + name + "'")
+
+
+def orders_for(cursor, user_id):
+    cursor.execute("SELECT * FROM orders WHERE user_id = " + user_id)
+
+
+def invoices_for(cursor, user_id):
+    cursor.execute("SELECT * FROM invoices WHERE user_id = " + user_id)
+
+
+def refunds_for(cursor, user_id):
+    cursor.execute("SELECT * FROM refunds WHERE user_id = " + user_id)
+
+
+def sessions_for(cursor, user_id):
+    cursor.execute("SELECT * FROM sessions WHERE user_id = " + user_id)
+
+
+def audit_for(cursor, user_id):
+    cursor.execute("SELECT * FROM audit WHERE user_id = " + user_id)
@@ -0,0 +1,20 @@
+// A dashboard client: one export nothing imports, plus patterns that only
+// opt-in modules report (swallowed errors, floating promises, magic numbers)
+
+export async function loadDashboard(id: string): Promise<unknown> {
+  try {
+    const response = await fetch("/api/dashboards/" + id);
+    return await response.json();
+  } catch (e) {}
+  return null;
+}
+
+export function refreshEvery(id: string): void {
+  setInterval(() => {
+    loadDashboard(id);
+  }, 86400);
+}
+
+export function unusedFormatter(value: number): string {
+  return (value * 1.0825).toFixed(2);
+}
@@ -0,0 +1,3 @@
+import { refreshEvery } from "./client";
+
+refreshEvery("home");
@@ -0,0 +1,5 @@
+import { render } from "./view";
+
+export function update(state: string): void {
+  render(state);
+}
@@ -0,0 +1,7 @@
+import { update } from "./store";
+
+export function render(state: string): void {
+  if (state === "stale") {
+    update("fresh");
+  }
+}
@@ -0,0 +1,7 @@
+"""Greeter service built on the generated messages."""
+
+from api_pb2 import Greeting
+
+
+def greet(name):
+    return Greeting(name)
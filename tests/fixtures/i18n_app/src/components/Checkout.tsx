@@ -0,0 +1,12 @@
+import { Trans, useTranslation } from "react-i18next";
+
+export function Checkout({ amount, count }: { amount: number; count: number }) {
+  const { t } = useTranslation();
+  return (
+    <section>
+      <h1>{t("checkout.title")}</h1>
+      <p>{t("checkout.items", { count })}</p>
+      <button>{t("checkout.pay", { amount })}</button>
+    </section>
+  );
+}
@@ -0,0 +1,12 @@
+import { Trans } from "react-i18next";
+import { useTranslation } from "react-i18next";
+
+export function Nav() {
+  const { t } = useTranslation();
+  return (
+    <nav>
+      <Trans i18nKey="nav.home" />
+      <a href="/account">{t(`nav.account`)}</a>
+    </nav>
+  );
+}
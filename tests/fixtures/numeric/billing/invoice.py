@@ -0,0 +1,17 @@
+"""Invoice totals, computed in floats"""
+
+
+def line_total(unit_price: float, quantity: int) -> float:
+    return unit_price * quantity
+
+
+class Invoice:
+    def __init__(self, subtotal: float, tax_rate: float):
+        self.subtotal = subtotal
+        self.tax: float = subtotal * tax_rate
+
+    def grand_total(self, shipping_fee=4.99):
+        total = self.subtotal + self.tax + shipping_fee
+        if total == 100.0:
+            print("free gift")
+        return total
@@ -0,0 +1,20 @@
+"""Ledger balances, kept in Decimal"""
+
+from decimal import Decimal
+
+
+def line_total(unit_price: Decimal, quantity: int) -> Decimal:
+    return unit_price * quantity
+
+
+class Account:
+    def __init__(self, balance_cents: int):
+        self.balance_cents = balance_cents
+        self.balance = Decimal(balance_cents) / 100
+        self.interest_rate: float = 0.015
+
+    def is_empty(self) -> bool:
+        return self.balance == Decimal("0")
+
+    def ratio(self, weight: float) -> float:
+        return weight / 3
@@ -0,0 +1,11 @@
+public class Invoice
+{
+    public double Price { get; set; }
+    public decimal Total { get; set; }
+
+    public void Apply(float discount)
+    {
+        decimal fee = 1.5m;
+        var amount = 2.5;
+    }
+}
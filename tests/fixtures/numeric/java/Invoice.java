@@ -0,0 +1,14 @@
+import java.math.BigDecimal;
+
+public class Invoice {
+    private double amount;
+    private BigDecimal tax = new BigDecimal("0.00");
+
+    public Invoice(double amount) {
+        this.amount = amount;
+    }
+
+    public boolean isRefund(double ratio) {
+        return ratio != 1.0;
+    }
+}
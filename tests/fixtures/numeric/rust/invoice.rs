@@ -0,0 +1,9 @@
+pub struct Invoice {
+    pub balance: f64,
+    pub balance_cents: i64,
+}
+
+pub fn cost(hours: f32) -> f64 {
+    let cost: f64 = f64::from(hours) * 80.0;
+    cost
+}
@@ -0,0 +1,7 @@
+from billing.invoice import Invoice
+
+
+def test_grand_total():
+    price: float = 10.0
+    invoice = Invoice(price, 0.2)
+    assert invoice.grand_total(0.0) == 12.0
@@ -0,0 +1,10 @@
+// Reads tweets from the API
+
+export const pinnedTweetId = 1445078208190291968;
+export const user = { id: 9007199254740993, name: "revet" };
+
+export function load(body) {
+  const { id, text } = JSON.parse(body);
+  const authorId = JSON.parse(body).author_id;
+  return { id, text, authorId };
+}
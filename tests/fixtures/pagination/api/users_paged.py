@@ -0,0 +1,36 @@
+from fastapi import APIRouter, Depends
+from sqlalchemy.orm import Session
+
+from .db import get_db
+from .models import User
+from .schemas import UserOut
+
+router = APIRouter()
+
+
+@router.get("/users")
+def list_users(limit: int = 50, offset: int = 0, db: Session = Depends(get_db)):
+    return db.query(User).offset(offset).limit(limit).all()
+
+
+@router.get("/users/active")
+def list_active_users(db: Session = Depends(get_db)):
+    users = db.query(User).filter(User.active).all()
+    return [UserOut.from_orm(u) for u in users[:100]]
+
+
+@router.get("/users/export")
+def export_users(db: Session = Depends(get_db)):
+    rows = db.execute("SELECT id, email FROM users ORDER BY id LIMIT 1000").fetchall()
+    return {"users": rows}
+
+
+@router.get("/users/count")
+def count_users(db: Session = Depends(get_db)):
+    users = db.query(User).all()
+    return {"count": len(users)}
+
+
+@router.get("/users/{user_id}")
+def get_user(user_id: int, db: Session = Depends(get_db)):
+    return db.query(User).filter(User.id == user_id).first()
@@ -0,0 +1,22 @@
+from rest_framework import viewsets
+from rest_framework.response import Response
+
+from .models import Order
+from .serializers import OrderSerializer
+
+
+class OrderViewSet(viewsets.ViewSet):
+    def list(self, request):
+        orders = Order.objects.filter(customer=request.user)
+        serializer = OrderSerializer(orders, many=True)
+        return Response(serializer.data)
+
+    def retrieve(self, request, pk=None):
+        order = Order.objects.get(pk=pk)
+        return Response(OrderSerializer(order).data)
+
+
+class RecentOrderViewSet(viewsets.ViewSet):
+    def list(self, request):
+        orders = Order.objects.filter(customer=request.user).order_by("-created")[:20]
+        return Response(OrderSerializer(orders, many=True).data)
@@ -0,0 +1,20 @@
+const fastify = require("fastify")();
+const { pool } = require("./db");
+
+fastify.get("/reports", async (request, reply) => {
+  const result = await pool.query("SELECT * FROM reports WHERE archived = false");
+  return result;
+});
+
+fastify.get("/reports/paged", async (request, reply) => {
+  const { limit, offset } = request.query;
+  const result = await pool.query("SELECT * FROM reports LIMIT $1 OFFSET $2", [limit, offset]);
+  return result.rows;
+});
+
+fastify.get("/reports/total", async (request, reply) => {
+  const result = await pool.query("SELECT count(*) FROM reports");
+  return result.rows[0];
+});
+
+module.exports = fastify;
@@ -0,0 +1,31 @@
+import express from "express";
+import { prisma } from "./db";
+
+const router = express.Router();
+
+router.get("/users", async (req, res) => {
+  const users = await prisma.user.findMany({ where: { active: true } });
+  res.json(users.map((u) => ({ id: u.id, email: u.email })));
+});
+
+router.get("/users/paged", async (req, res) => {
+  const take = Number(req.query.limit ?? 50);
+  const users = await prisma.user.findMany({ where: { active: true }, take });
+  res.json(users);
+});
+
+router.get("/posts", async (req, res) => {
+  const { cursor } = req.query;
+  const posts = await prisma.post.findMany({ cursor: { id: String(cursor) }, take: 20 });
+  res.json(posts);
+});
+
+router.get("/posts/all", async (req, res) => {
+  res.json(await prisma.post.findMany());
+});
+
+router.get("/users/:id", async (req, res) => {
+  res.json(await prisma.user.findUnique({ where: { id: req.params.id } }));
+});
+
+export default router;
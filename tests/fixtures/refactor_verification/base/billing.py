@@ -0,0 +1,6 @@
+def charge(order):
+    return order.total
+
+
+def refund_amount(order):
+    return order.total - order.fees
@@ -0,0 +1,5 @@
+from billing import charge
+
+
+def checkout(order):
+    return charge(order)
@@ -0,0 +1,5 @@
+import { price } from "./pricing";
+
+export function cartTotal(amounts: number[]): number {
+  return amounts.reduce((sum, amount) => sum + price(amount), 0);
+}
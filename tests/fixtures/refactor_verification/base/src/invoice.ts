@@ -0,0 +1,5 @@
+import { price } from "./pricing";
+
+export function invoiceLine(amount: number): string {
+  return `total: ${price(amount)}`;
+}
@@ -0,0 +1,3 @@
+export function price(amount: number): number {
+  return amount;
+}
@@ -0,0 +1,6 @@
+def charge(order, currency):
+    return order.total * currency.rate
+
+
+def refundable_amount(order):
+    return order.total - order.fees
@@ -0,0 +1,5 @@
+from billing import charge
+
+
+def checkout(order, currency):
+    return charge(order, currency)
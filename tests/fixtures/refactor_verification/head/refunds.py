@@ -0,0 +1,6 @@
+from billing import charge, refund_amount
+
+
+def refund(order):
+    charge(order)
+    return refund_amount(order)
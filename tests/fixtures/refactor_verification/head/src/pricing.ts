@@ -0,0 +1,3 @@
+export function price(amount: number, taxRate: number): number {
+  return amount * (1 + taxRate);
+}
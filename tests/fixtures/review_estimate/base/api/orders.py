@@ -0,0 +1,10 @@
+def total(order):
+    amount = 0
+    for item in order.items:
+        if item.free:
+            continue
+        elif item.bulk:
+            amount += item.price * 0.9
+        else:
+            amount += item.price
+    return amount
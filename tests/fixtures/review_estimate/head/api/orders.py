@@ -0,0 +1,13 @@
+CURRENCY = "EUR"
+
+
+def total(order):
+    amount = 0
+    for item in order.items:
+        if item.free:
+            continue
+        elif item.bulk:
+            amount += item.price * 0.85
+        else:
+            amount += item.price * item.quantity
+    return amount
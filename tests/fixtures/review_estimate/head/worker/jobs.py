@@ -0,0 +1,2 @@
+def retry_delay(attempt):
+    return 3 * attempt
@@ -0,0 +1,17 @@
+// API client with annotated suppressions
+
+const key = "AKIAIOSFODNN7EXAMPLE1"; // revet-ignore SEC SQL
+
+// revet-ignore:SEC
+export const region = "eu-west-1";
+
+export const retries = 3; // revet-ignore SEC-001
+
+// revet-ignore
+export const timeout = 30;
+
+export const token = "AKIAI44QH8DHBEXAMPLE"; // revet-ignore SQL, SEC
+
+export function url(): string {
+  return key;
+}
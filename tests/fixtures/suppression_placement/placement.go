@@ -0,0 +1,18 @@
+// Package placement shows revet-ignore comments in Go
+package placement
+
+const url = `http://host // revet-ignore SEC`
+const raw = `
+// revet-ignore SEC
+`
+const key = "AKIA" /* revet-ignore SEC */
+
+// revet-ignore:block SEC
+func Load() string {
+	token := "AKIA"
+	return token
+}
+
+func Other() rune {
+	return '/' // revet-ignore SEC
+}
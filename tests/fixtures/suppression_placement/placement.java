@@ -0,0 +1,7 @@
+// Placement of revet-ignore comments in Java
+class Placement {
+    String block = """
+        // revet-ignore SEC
+        """;
+    String url = "http://host"; // revet-ignore SEC
+}
@@ -0,0 +1,39 @@
+"""Placement of revet-ignore comments in Python."""
+
+key = "AKIA"  # revet-ignore SEC
+after_trailing = 1
+
+# revet-ignore SEC
+
+# an unrelated comment
+standalone = 2
+after_standalone = 3
+
+url = "http://host/#anchor"  # revet-ignore SQL
+quoted = "# revet-ignore SEC"
+formatted = f"{key}#{url:#>10} revet-ignore SEC"
+nested = f"{'#'} // revet-ignore SEC"
+doc = """
+# revet-ignore SEC
+"""
+
+
+# revet-ignore SEC
+@app.route(
+    "/debug",
+)
+def debug():
+    return key
+
+
+# revet-ignore:block SEC
+@cached
+class Settings:
+    token = "AKIA"
+
+    def load(self):
+        return self.token
+
+
+def outside():
+    return key  # revet-ignore SEC
@@ -0,0 +1,19 @@
+//! Placement of revet-ignore comments in Rust
+
+const URL: &str = r#"http://host "// revet-ignore SEC"#;
+const SLASH: char = '/'; // revet-ignore SEC
+
+fn first<'a>(s: &'a str) -> &'a str { s } // revet-ignore SEC
+
+// revet-ignore SEC
+#[inline]
+#[must_use]
+fn key() -> &'static str {
+    "AKIA"
+}
+
+/* revet-ignore:block SEC */
+fn load() -> String {
+    let quote = '"';
+    String::from("// not a comment")
+}
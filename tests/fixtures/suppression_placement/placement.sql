@@ -0,0 +1,5 @@
+-- Placement of revet-ignore comments in SQL
+SELECT '-- revet-ignore SEC' AS quoted;
+SELECT 'a--b' AS dashes; -- revet-ignore SEC
+-- revet-ignore SEC
+SELECT * FROM users;
@@ -0,0 +1,7 @@
+# Placement of revet-ignore comments in Terraform
+locals {
+  url    = "https://host/${var.path}#top" // revet-ignore SEC
+  quoted = "# revet-ignore SEC"
+  // revet-ignore SEC
+  key = "AKIA"
+}
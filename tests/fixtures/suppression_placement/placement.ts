@@ -0,0 +1,29 @@
+// Placement of revet-ignore comments in TypeScript
+
+const url = "https://host/path"; const key = "AKIA"; // revet-ignore SEC
+const next = 1;
+const quoted = "// revet-ignore SEC";
+const template = `http://${host}/${"//"} revet-ignore SEC`;
+const pattern = /\/\/ revet-ignore SEC/;
+const ratio = total / count; // revet-ignore SEC
+/* revet-ignore SEC */ const inline = 2;
+const multi = `
+// revet-ignore SEC
+`;
+
+/*
+ * revet-ignore SEC
+ */
+export const documented = 3;
+
+// revet-ignore:block SEC
+@Injectable()
+export class Service {
+  token = "AKIA";
+
+  load(): string {
+    return this.token;
+  }
+}
+
+export function after(): void {}
@@ -0,0 +1,14 @@
+import time
+
+
+def retry(call, attempts=3):
+    for attempt in range(attempts):
+        try:
+            return call()
+        except ConnectionError:
+            time.sleep(2 ** attempt)
+    raise ConnectionError("gave up")
+
+
+def test_connection(call):
+    call()
@@ -0,0 +1,14 @@
+describe("checkout", () => {
+  it("pays", () => {
+    cy.visit("/checkout");
+    cy.wait(1000);
+    cy.wait("@payment");
+    cy.get("[data-test=status]").should("have.text", "paid");
+  });
+});
+
+test("confirmation page", async ({ page }) => {
+  await page.goto("/confirmation");
+  await page.waitForTimeout(500);
+  await expect(page.getByRole("heading")).toHaveText("Thanks");
+});
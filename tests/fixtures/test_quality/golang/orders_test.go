@@ -0,0 +1,36 @@
+package orders
+
+import (
+	"os"
+	"testing"
+	"time"
+)
+
+func TestPlace(t *testing.T) {
+	order := Place(10)
+	time.Sleep(100 * time.Millisecond)
+	if order.Status != "placed" {
+		t.Errorf("status = %q, want placed", order.Status)
+	}
+}
+
+func TestPlaceSmoke(t *testing.T) {
+	Place(10)
+}
+
+func TestRefund(t *testing.T) {
+	t.Skip("refunds are not implemented")
+	if !Place(5).Refund() {
+		t.Fatal("refund failed")
+	}
+}
+
+func TestIntegration(t *testing.T) {
+	if os.Getenv("INTEGRATION") == "" {
+		t.Skip("set INTEGRATION to run")
+	}
+	order := Place(1)
+	if order == nil {
+		t.Fatal("no order")
+	}
+}
@@ -0,0 +1,32 @@
+import { Cart } from "../app/cart";
+
+describe("Cart", () => {
+  it("adds items", async () => {
+    const cart = new Cart();
+    cart.add("sku-1");
+    await new Promise((resolve) => setTimeout(resolve, 500));
+    expect(cart.size).toBe(1);
+  });
+
+  it("renders", () => {
+    new Cart().render();
+  });
+
+  it("rejects unknown items", () => {
+    expect(() => new Cart().add("")).toThrow();
+  });
+
+  it("rejects negative quantities", () => {
+    expect(() => new Cart().add("sku-1", -1)).toThrow(RangeError);
+  });
+
+  it.skip("applies coupons", () => {
+    expect(new Cart().applyCoupon("X")).toBe(true);
+  });
+
+  test("empties", () => {
+    const cart = new Cart();
+    cart.clear();
+    assert.equal(cart.size, 0);
+  });
+});
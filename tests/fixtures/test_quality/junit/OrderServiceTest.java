@@ -0,0 +1,40 @@
+package com.example.orders;
+
+import static org.junit.jupiter.api.Assertions.assertEquals;
+import static org.junit.jupiter.api.Assertions.assertThrows;
+import static org.mockito.Mockito.verify;
+
+import org.junit.jupiter.api.Disabled;
+import org.junit.jupiter.api.Test;
+
+class OrderServiceTest {
+    private final OrderService service = new OrderService();
+
+    @Test
+    void placesOrder() throws Exception {
+        Order order = service.place(10);
+        Thread.sleep(1000);
+        assertEquals("placed", order.status());
+    }
+
+    @Test
+    void placesOrderWithoutChecking() {
+        service.place(10);
+    }
+
+    @Test
+    void rejectsNegativeTotal() {
+        assertThrows(Exception.class, () -> service.place(-1));
+    }
+
+    @Test
+    void rejectsEmptyOrder() {
+        assertThrows(IllegalArgumentException.class, () -> service.place(0));
+    }
+
+    @Disabled("until the payment sandbox is back")
+    @Test
+    void refunds() {
+        verify(service.gateway()).refund(10);
+    }
+}
@@ -0,0 +1,18 @@
+package com.example.orders
+
+import kotlin.test.Ignore
+import kotlin.test.Test
+import kotlin.test.assertFailsWith
+
+class OrderTest {
+    @Test
+    fun rejectsNegativeTotal() {
+        assertFailsWith<Exception> { place(-1) }
+    }
+
+    @Ignore
+    @Test
+    fun refunds() {
+        assertFailsWith<IllegalStateException> { refund(0) }
+    }
+}
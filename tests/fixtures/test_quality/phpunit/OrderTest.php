@@ -0,0 +1,19 @@
+<?php
+
+use PHPUnit\Framework\TestCase;
+
+final class OrderTest extends TestCase
+{
+    public function testPlace(): void
+    {
+        $order = Orders::place(10);
+        sleep(1);
+        $this->assertSame('placed', $order->status);
+    }
+
+    public function testRejectsNegativeTotal(): void
+    {
+        $this->expectException(\Exception::class);
+        Orders::place(-1);
+    }
+}
@@ -0,0 +1,38 @@
+import asyncio
+import time
+
+import pytest
+
+from app.orders import Order, place
+
+
+def test_place_order():
+    order = place(Order(total=10))
+    time.sleep(2)
+    assert order.status == "placed"
+
+
+def test_place_order_smoke():
+    place(Order(total=10))
+
+
+def test_rejects_negative_total():
+    with pytest.raises(Exception):
+        place(Order(total=-1))
+
+
+def test_rejects_empty_order():
+    with pytest.raises(ValueError, match="empty"):
+        place(Order(total=0))
+
+
+@pytest.mark.skip(reason="flaky on CI")
+def test_refund():
+    assert place(Order(total=5)).refund()
+
+
+@pytest.mark.asyncio
+async def test_async_place():
+    await asyncio.sleep(0)
+    order = await place_async(Order(total=3))
+    assert order.id
@@ -0,0 +1,25 @@
+require "order"
+
+RSpec.describe Order do
+  it "places" do
+    order = Order.place(10)
+    sleep 1
+    expect(order.status).to eq("placed")
+  end
+
+  it "places without checking" do
+    Order.place(10)
+  end
+
+  it "rejects negative totals" do
+    expect { Order.place(-1) }.to raise_error
+  end
+
+  it "rejects empty orders" do
+    expect { Order.place(0) }.to raise_error(ArgumentError)
+  end
+
+  xit "refunds" do
+    expect(Order.place(5).refund).to be(true)
+  end
+end
@@ -0,0 +1,34 @@
+use std::thread;
+use std::time::Duration;
+
+use orders::{place, Order};
+
+#[test]
+fn places_order() {
+    let order = place(Order::new(10));
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(order.status(), "placed");
+}
+
+#[test]
+fn places_order_smoke() {
+    place(Order::new(10));
+}
+
+#[test]
+#[should_panic]
+fn rejects_negative_total() {
+    place(Order::new(-1));
+}
+
+#[test]
+#[should_panic(expected = "empty order")]
+fn rejects_empty_order() {
+    place(Order::new(0));
+}
+
+#[test]
+#[ignore]
+fn refunds() {
+    place(Order::new(5)).refund().unwrap();
+}
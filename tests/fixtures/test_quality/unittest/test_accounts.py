@@ -0,0 +1,21 @@
+import unittest
+
+from app.accounts import Account
+
+
+class AccountTest(unittest.TestCase):
+    def test_withdraw(self):
+        account = Account(balance=10)
+        account.withdraw(5)
+        self.assertEqual(account.balance, 5)
+
+    def test_overdraw(self):
+        with self.assertRaises(Exception):
+            Account(balance=0).withdraw(1)
+
+    def test_deposit(self):
+        Account(balance=0).deposit(5)
+
+    @unittest.skip("waiting on the ledger rewrite")
+    def test_transfer(self):
+        self.assertTrue(Account(balance=1).transfer(1))
@@ -0,0 +1,10 @@
+import { createCipher, createHash } from 'crypto';
+
+export function fingerprint(secret) {
+  return createHash('sha256').update(secret).digest('hex');
+}
+
+export function seal(secret, password) {
+  const cipher = createCipher('aes-256-cbc', password);
+  return cipher.update(secret, 'utf8', 'hex') + cipher.final('hex');
+}
@@ -0,0 +1,17 @@
+"""Release helpers: version comparison and the publish loop."""
+
+import asyncio
+from distutils.version import LooseVersion
+
+
+def newer(current, candidate):
+    return LooseVersion(candidate) > LooseVersion(current)
+
+
+async def publish(artifacts):
+    for artifact in artifacts:
+        await artifact.upload()
+
+
+def main(artifacts):
+    asyncio.run(publish(artifacts))
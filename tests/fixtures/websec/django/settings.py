@@ -0,0 +1,16 @@
+import os
+
+DEBUG = False
+
+MIDDLEWARE = [
+    "django.middleware.common.CommonMiddleware",
+    "corsheaders.middleware.CorsMiddleware",
+]
+
+CORS_ALLOW_ALL_ORIGINS = True
+CORS_ALLOW_CREDENTIALS = True
+
+SESSION_COOKIE_SECURE = False
+CSRF_COOKIE_SAMESITE = None
+SECURE_HSTS_SECONDS = 0
+SESSION_COOKIE_HTTPONLY = os.environ.get("SESSION_COOKIE_HTTPONLY") == "1"
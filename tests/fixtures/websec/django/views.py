@@ -0,0 +1,14 @@
+from django.conf import settings
+from django.http import HttpResponse
+
+
+def set_theme(request):
+    response = HttpResponse("ok")
+    response.set_cookie("theme", "dark", httponly=True, secure=True, samesite="Strict")
+    return response
+
+
+def remember(request):
+    response = HttpResponse("ok")
+    response.set_cookie("remember", request.POST["token"], httponly=True, secure=True, **settings.COOKIE_OPTIONS)
+    return response
@@ -0,0 +1,15 @@
+const express = require("express");
+const cors = require("cors");
+const session = require("express-session");
+
+const app = express();
+
+app.use(cors({ origin: "*", credentials: true }));
+app.use(session({ secret: process.env.SESSION_SECRET, cookie: { httpOnly: false, secure: true, sameSite: "lax" } }));
+
+app.post("/login", (req, res) => {
+  res.cookie("sid", req.sessionID);
+  res.json({ ok: true });
+});
+
+module.exports = app;
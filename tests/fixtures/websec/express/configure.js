@@ -0,0 +1,15 @@
+const cors = require("cors");
+const config = require("./config");
+
+module.exports = function configure(app) {
+  app.use(cors({ origin: config.allowedOrigins, credentials: true }));
+
+  app.post("/remember", (req, res) => {
+    res.cookie("remember", req.body.token, {
+      httpOnly: true,
+      secure: config.secureCookies,
+      sameSite: "lax",
+    });
+    res.end();
+  });
+};
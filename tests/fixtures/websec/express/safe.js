@@ -0,0 +1,17 @@
+const express = require("express");
+const cors = require("cors");
+const helmet = require("helmet");
+const session = require("express-session");
+
+const app = express();
+
+app.use(helmet());
+app.use(cors({ origin: ["https://app.example.com"], credentials: true }));
+app.use(session({ secret: process.env.SESSION_SECRET, cookie: { secure: true, sameSite: "lax" } }));
+
+app.post("/login", (req, res) => {
+  res.cookie("sid", req.sessionID, { httpOnly: true, secure: true, sameSite: "strict" });
+  res.json({ ok: true });
+});
+
+module.exports = app;
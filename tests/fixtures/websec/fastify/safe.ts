@@ -0,0 +1,15 @@
+import Fastify from "fastify";
+import cors from "@fastify/cors";
+import helmet from "@fastify/helmet";
+
+const fastify = Fastify({ logger: true });
+
+fastify.register(helmet);
+fastify.register(cors, { origin: "https://app.example.com", credentials: true });
+
+fastify.get("/session", async (request, reply) => {
+  reply.setCookie("token", "abc", { httpOnly: true, secure: true, sameSite: "strict", path: "/" });
+  return { ok: true };
+});
+
+export default fastify;
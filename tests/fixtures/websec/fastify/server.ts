@@ -0,0 +1,15 @@
+import Fastify from "fastify";
+import cors from "@fastify/cors";
+import helmet from "@fastify/helmet";
+
+const fastify = Fastify({ logger: true });
+
+fastify.register(cors, { origin: true, credentials: true });
+fastify.register(helmet, { contentSecurityPolicy: false });
+
+fastify.get("/session", async (request, reply) => {
+  reply.setCookie("token", "abc", { httpOnly: true, secure: true, sameSite: false });
+  return { ok: true };
+});
+
+export default fastify;
@@ -0,0 +1,13 @@
+from flask import Flask, make_response
+from flask_cors import CORS
+
+app = Flask(__name__)
+app.config["SESSION_COOKIE_SECURE"] = False
+CORS(app, supports_credentials=True)
+
+
+@app.route("/login", methods=["POST"])
+def login():
+    response = make_response("ok")
+    response.set_cookie("sid", "token", secure=True, samesite="Lax")
+    return response
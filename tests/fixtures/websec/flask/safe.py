@@ -0,0 +1,15 @@
+from flask import Flask, make_response
+from flask_cors import CORS
+from flask_talisman import Talisman
+
+app = Flask(__name__)
+Talisman(app)
+app.config.update(SESSION_COOKIE_SECURE=True, SESSION_COOKIE_SAMESITE="Lax")
+CORS(app, origins=["https://app.example.com"], supports_credentials=True)
+
+
+@app.route("/login", methods=["POST"])
+def login():
+    response = make_response("ok")
+    response.set_cookie("sid", "token", httponly=True, secure=True, samesite="Lax")
+    return response
@@ -0,0 +1,8 @@
+class SessionsController < ApplicationController
+  def create
+    cookies[:remember_token] = { value: params[:token], httponly: true }
+    cookies.signed[:user_id] = { value: current_user.id, httponly: true, secure: Rails.env.production? }
+    cookies[:theme] = { value: "dark", httponly: true, secure: true }
+    head :ok
+  end
+end
@@ -0,0 +1,13 @@
+module Shop
+  class Application < Rails::Application
+    config.force_ssl = false
+    config.session_store :cookie_store, key: "_shop_session", secure: false
+
+    config.middleware.insert_before 0, Rack::Cors do
+      allow do
+        origins "*"
+        resource "*", headers: :any, methods: [:get, :post], credentials: true
+      end
+    end
+  end
+end
@@ -0,0 +1,4 @@
+Rails.application.configure do
+  config.force_ssl = true
+  config.session_store :cookie_store, key: "_shop_session", secure: true, same_site: :lax
+end
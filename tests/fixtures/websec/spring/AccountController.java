@@ -0,0 +1,19 @@
+package com.example.web;
+
+import jakarta.servlet.http.Cookie;
+import jakarta.servlet.http.HttpServletResponse;
+import org.springframework.web.bind.annotation.CrossOrigin;
+import org.springframework.web.bind.annotation.PostMapping;
+import org.springframework.web.bind.annotation.RestController;
+
+@RestController
+@CrossOrigin(allowCredentials = "true")
+public class AccountController {
+
+    @PostMapping("/remember")
+    public void remember(HttpServletResponse response) {
+        Cookie cookie = new Cookie("remember", "token");
+        cookie.setHttpOnly(true);
+        response.addCookie(cookie);
+    }
+}
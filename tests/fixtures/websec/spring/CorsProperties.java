@@ -0,0 +1,16 @@
+package com.example.web;
+
+import org.springframework.beans.factory.annotation.Value;
+import org.springframework.web.servlet.config.annotation.CorsRegistry;
+import org.springframework.web.servlet.config.annotation.WebMvcConfigurer;
+
+public class CorsProperties implements WebMvcConfigurer {
+
+    @Value("${cors.origins}")
+    private String[] origins;
+
+    @Override
+    public void addCorsMappings(CorsRegistry registry) {
+        registry.addMapping("/**").allowedOrigins(origins).allowCredentials(true);
+    }
+}
@@ -0,0 +1,24 @@
+package com.example.web;
+
+import org.springframework.http.ResponseCookie;
+import org.springframework.security.config.annotation.web.builders.HttpSecurity;
+import org.springframework.security.web.SecurityFilterChain;
+import org.springframework.web.servlet.config.annotation.CorsRegistry;
+import org.springframework.web.servlet.config.annotation.WebMvcConfigurer;
+
+public class SafeConfig implements WebMvcConfigurer {
+
+    @Override
+    public void addCorsMappings(CorsRegistry registry) {
+        registry.addMapping("/api/**").allowedOrigins("https://app.example.com").allowCredentials(true);
+    }
+
+    SecurityFilterChain filterChain(HttpSecurity http) throws Exception {
+        http.headers(headers -> headers.frameOptions(frame -> frame.sameOrigin()));
+        return http.build();
+    }
+
+    ResponseCookie sessionCookie(String value) {
+        return ResponseCookie.from("SESSION", value).httpOnly(true).secure(true).sameSite("Lax").build();
+    }
+}
@@ -0,0 +1,32 @@
+package com.example.web;
+
+import java.util.List;
+import org.springframework.context.annotation.Bean;
+import org.springframework.http.ResponseCookie;
+import org.springframework.security.config.annotation.web.builders.HttpSecurity;
+import org.springframework.security.web.SecurityFilterChain;
+import org.springframework.web.cors.CorsConfiguration;
+
+public class SecurityConfig {
+
+    @Bean
+    SecurityFilterChain filterChain(HttpSecurity http) throws Exception {
+        http.headers(headers -> headers.frameOptions(frame -> frame.disable()));
+        return http.build();
+    }
+
+    @Bean
+    CorsConfiguration corsConfiguration() {
+        CorsConfiguration config = new CorsConfiguration();
+        config.setAllowedOriginPatterns(List.of("*"));
+        config.setAllowCredentials(true);
+        return config;
+    }
+
+    ResponseCookie sessionCookie(String value) {
+        return ResponseCookie.from("SESSION", value)
+            .httpOnly(true)
+            .secure(false)
+            .build();
+    }
+}